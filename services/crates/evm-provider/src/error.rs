@@ -13,6 +13,7 @@
 //! - **Chain-agnostic**: Same error types regardless of the underlying chain
 
 use alloy::primitives::{Address, TxHash};
+use sensitive::scrub_echo;
 use std::time::Duration;
 use thiserror::Error;
 
@@ -30,9 +31,10 @@ pub type Result<T> = std::result::Result<T, ProviderError>;
 /// | Category | Variants | Typical Cause |
 /// |----------|----------|---------------|
 /// | Network | `Connection`, `Timeout` | Network issues, server down |
-/// | Protocol | `Rpc`, `Unsupported` | Server rejected request |
+/// | Protocol | `Rpc`, `RateLimited`, `Unsupported` | Server rejected request |
 /// | Transaction | `TransactionFailed`, `NonceTooLow` | Tx execution issues |
 /// | Data | `InvalidResponse`, `Encoding` | Malformed data |
+/// | Signing | `Signing` | Incomplete request, signer refused |
 /// | Configuration | `InvalidConfig` | Programmer error |
 #[derive(Debug, Error)]
 #[non_exhaustive]
@@ -60,6 +62,12 @@ pub enum ProviderError {
         message: String,
     },
 
+    /// The endpoint rejected the request for exceeding its rate limit.
+    ///
+    /// Usually an HTTP 429 response; back off before sending more requests.
+    #[error("rate limited: {0}")]
+    RateLimited(String),
+
     /// The requested operation is not supported by this provider or chain.
     ///
     /// This is common when using chain-specific features on standard providers.
@@ -96,6 +104,12 @@ pub enum ProviderError {
         actual: u64,
     },
 
+    /// A transaction could not be signed.
+    ///
+    /// The request lacked a field signing needs, or the signer refused it.
+    #[error("signing failed: {0}")]
+    Signing(String),
+
     /// Failed to encode or decode transaction data.
     #[error("encoding error: {0}")]
     Encoding(String),
@@ -130,11 +144,15 @@ pub enum ProviderError {
 
 impl ProviderError {
     /// Create an RPC error from code and message.
+    ///
+    /// Long hex payloads the node echoed from the request, such as a raw
+    /// transaction, are cut from the message, and what looks like a key is
+    /// redacted.
     #[must_use]
     pub fn rpc(code: i64, message: impl Into<String>) -> Self {
         Self::Rpc {
             code,
-            message: message.into(),
+            message: scrub_echo(&message.into()),
         }
     }
 
@@ -150,7 +168,7 @@ impl ProviderError {
     #[must_use]
     pub const fn is_retryable(&self) -> bool {
         match self {
-            Self::Connection(_) | Self::Timeout(_) => true,
+            Self::Connection(_) | Self::Timeout(_) | Self::RateLimited(_) => true,
             Self::Rpc { code, .. } => {
                 // Server overloaded or rate limited
                 *code == -32005  // Limit exceeded
@@ -160,6 +178,42 @@ impl ProviderError {
         }
     }
 
+    /// Check if the endpoint is throttling requests.
+    ///
+    /// Returns `true` for HTTP 429 responses and the JSON-RPC "limit
+    /// exceeded" error (-32005). Rate limits are retryable, but only after
+    /// backing off.
+    #[must_use]
+    pub const fn is_rate_limited(&self) -> bool {
+        matches!(self, Self::RateLimited(_) | Self::Rpc { code: -32005, .. })
+    }
+
+    /// Check if a node refused a transaction after checking it, for too
+    /// little gas, fees or funds, or a used nonce.
+    ///
+    /// Such a refusal shows the transaction reached a node that validates
+    /// what it is sent. Like [`revert_reason`](Self::revert_reason), this is
+    /// string-based for JSON-RPC errors.
+    #[must_use]
+    pub fn is_refusal(&self) -> bool {
+        const REFUSALS: [&str; 6] = [
+            "intrinsic gas too low",
+            "insufficient funds",
+            "nonce too low",
+            "underpriced",
+            "fee cap less than block base fee",
+            "exceeds block gas limit",
+        ];
+        match self {
+            Self::NonceTooLow { .. } | Self::InsufficientBalance { .. } => true,
+            Self::Rpc { message, .. } => {
+                let message = message.to_ascii_lowercase();
+                REFUSALS.iter().any(|refusal| message.contains(refusal))
+            }
+            _ => false,
+        }
+    }
+
     /// Check if this is a nonce-related error that can be fixed by resync.
     #[must_use]
     pub const fn is_nonce_error(&self) -> bool {
@@ -171,6 +225,29 @@ impl ProviderError {
     pub const fn is_insufficient_balance(&self) -> bool {
         matches!(self, Self::InsufficientBalance { .. })
     }
+
+    /// Reason a call or gas estimate reverted with, if this error reports
+    /// an execution revert.
+    ///
+    /// The reason is empty if the revert carried none. Like the alloy error
+    /// conversion, this is string-based: nodes report reverts as JSON-RPC
+    /// errors with an "execution reverted" message.
+    #[must_use]
+    pub fn revert_reason(&self) -> Option<String> {
+        revert_reason(&self.to_string()).map(str::to_string)
+    }
+}
+
+/// Reason reported after "execution reverted" in an error `message`, empty
+/// if there is none.
+#[must_use]
+pub fn revert_reason(message: &str) -> Option<&str> {
+    const REVERTED: &str = "execution reverted";
+    let at = message.to_ascii_lowercase().rfind(REVERTED)?;
+    Some(
+        message[at + REVERTED.len()..]
+            .trim_matches(|c: char| c == ':' || c == ',' || c.is_whitespace()),
+    )
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -183,8 +260,14 @@ impl From<alloy::transports::TransportError> for ProviderError {
         // Note: This is string-based because alloy doesn't expose structured error types
         let msg = err.to_string();
         let msg_lower = msg.to_lowercase();
+        let msg = scrub_echo(&msg);
 
-        if msg_lower.contains("timeout") || msg_lower.contains("timed out") {
+        if msg_lower.contains("429")
+            || msg_lower.contains("too many requests")
+            || msg_lower.contains("rate limit")
+        {
+            Self::RateLimited(msg)
+        } else if msg_lower.contains("timeout") || msg_lower.contains("timed out") {
             // Use Connection variant to preserve the original message since we
             // don't know the actual timeout duration
             Self::Connection(format!("request timed out: {msg}"))
@@ -234,6 +317,16 @@ mod tests {
         assert!(!unsupported.is_retryable());
     }
 
+    #[test]
+    fn error_is_rate_limited() {
+        assert!(ProviderError::RateLimited("HTTP 429".into()).is_rate_limited());
+        assert!(ProviderError::rpc(-32005, "limit exceeded").is_rate_limited());
+        assert!(ProviderError::RateLimited("HTTP 429".into()).is_retryable());
+
+        assert!(!ProviderError::rpc(-32000, "server error").is_rate_limited());
+        assert!(!ProviderError::Timeout(Duration::from_secs(30)).is_rate_limited());
+    }
+
     #[test]
     fn error_is_nonce_error() {
         let nonce_low = ProviderError::NonceTooLow {
@@ -259,4 +352,41 @@ mod tests {
         let timeout = ProviderError::Timeout(Duration::from_secs(30));
         assert!(!timeout.is_insufficient_balance());
     }
+
+    #[test]
+    fn error_is_refusal() {
+        let refused = ProviderError::rpc(-32000, "intrinsic gas too low: have 1, want 21000");
+        assert!(refused.is_refusal());
+        assert!(ProviderError::rpc(-32000, "replacement transaction underpriced").is_refusal());
+
+        assert!(!ProviderError::rpc(-32000, "sequencer unavailable").is_refusal());
+        assert!(!ProviderError::RateLimited("HTTP 429".into()).is_refusal());
+        assert!(!ProviderError::Connection("connection refused".into()).is_refusal());
+    }
+
+    #[test]
+    fn decodes_revert_reasons() {
+        let reverted = ProviderError::rpc(3, "execution reverted: PositionLocked");
+        assert_eq!(reverted.revert_reason().as_deref(), Some("PositionLocked"));
+        let bare = ProviderError::Other("server returned an error: Execution reverted".into());
+        assert_eq!(bare.revert_reason().as_deref(), Some(""));
+
+        let timeout = ProviderError::Timeout(Duration::from_secs(30));
+        assert_eq!(timeout.revert_reason(), None);
+    }
+    #[test]
+    fn echoed_requests_are_scrubbed() {
+        let raw_tx = format!("0x02f8b1{}", "ab".repeat(180));
+        let error = ProviderError::rpc(-32000, format!("invalid transaction: {raw_tx}"));
+        assert!(
+            error
+                .to_string()
+                .ends_with("invalid transaction: 0x02f8b1abab…[356 hex digits]"),
+            "{error}"
+        );
+
+        let key = "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+        let error = ProviderError::rpc(-32000, format!("invalid signer key {key}"));
+        assert!(error.to_string().ends_with("invalid signer key [redacted]"), "{error}");
+    }
 }
@@ -0,0 +1,692 @@
+//! Failover across several RPC endpoints of the same chain.
+//!
+//! This module provides [`FailoverProvider`], a [`ChainProvider`] that wraps
+//! an ordered list of providers for the same chain. Each request goes to the
+//! most preferred healthy endpoint; when that fails with an error another
+//! endpoint could avoid (connection failures, rate limits, overloaded
+//! servers), the endpoint is marked unhealthy and the request moves on to
+//! the next one.
+//!
+//! Unhealthy endpoints are restored by health probes, either run by hand
+//! with [`FailoverProvider::probe_unhealthy`] or in the background with
+//! [`FailoverProvider::spawn_health_probes`].
+//!
+//! # Example
+//!
+//! ```ignore
+//! use evm_provider::{FailoverProvider, StandardEvmProvider};
+//!
+//! let provider = Arc::new(FailoverProvider::new(vec![
+//!     ("primary".to_string(), StandardEvmProvider::new("https://carrot.megaeth.com/rpc").await?),
+//!     ("backup".to_string(), StandardEvmProvider::new("https://backup.example/rpc").await?),
+//! ])?);
+//! let probes = provider.spawn_health_probes(Duration::from_secs(15));
+//!
+//! let balance = provider.get_balance(address).await?;
+//! println!("Served by {}", provider.current_endpoint());
+//! ```
+//!
+//! # Nonce Consistency
+//!
+//! Endpoints of a load-balanced chain do not always agree on the latest
+//! state, so reading a nonce from one endpoint and sending to another can
+//! make a wallet's nonces look inconsistent. Nonce-sensitive requests (nonce
+//! reads, sends and receipts) therefore stick to the endpoint that last
+//! served one of them for [`sticky_window`](FailoverProvider::with_sticky_window),
+//! even if a more preferred endpoint recovered in the meantime.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError, Weak};
+use std::time::{Duration, Instant};
+
+use alloy::primitives::{Address, Bytes, TxHash, U256};
+use async_trait::async_trait;
+use futures::future::BoxFuture;
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+use crate::error::{ProviderError, Result};
+use crate::traits::{ChainProvider, ExtendedChainProvider};
+use crate::types::{LogFilter, LogsPage, TransactionReceipt, TransactionRequest};
+
+/// Default time nonce-sensitive requests stick to the same endpoint.
+const DEFAULT_STICKY_WINDOW: Duration = Duration::from_secs(30);
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// ENDPOINT HEALTH
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Health of one endpoint of a [`FailoverProvider`], for metrics.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EndpointHealth {
+    /// Name the endpoint was registered under.
+    pub name: String,
+
+    /// Whether requests are routed to the endpoint.
+    pub healthy: bool,
+
+    /// Failures since the endpoint last served a request or passed a probe.
+    pub consecutive_failures: u32,
+
+    /// Requests the endpoint served.
+    pub requests: u64,
+
+    /// Requests the endpoint failed.
+    pub failures: u64,
+
+    /// Most recent failure, if any.
+    pub last_error: Option<String>,
+}
+
+/// Mutable health state of an endpoint.
+#[derive(Debug, Default)]
+struct HealthState {
+    /// Whether the endpoint was marked unhealthy.
+    unhealthy: bool,
+
+    /// Failures since the last success.
+    consecutive_failures: u32,
+
+    /// Most recent failure.
+    last_error: Option<String>,
+}
+
+/// One endpoint of a [`FailoverProvider`].
+#[derive(Debug)]
+struct Endpoint<P> {
+    /// Name for logs and metrics, e.g. the host.
+    name: String,
+
+    /// The wrapped provider.
+    provider: P,
+
+    /// Health state.
+    health: Mutex<HealthState>,
+
+    /// Requests served.
+    requests: AtomicU64,
+
+    /// Requests failed.
+    failures: AtomicU64,
+}
+
+impl<P> Endpoint<P> {
+    /// Whether requests are routed to the endpoint.
+    fn is_healthy(&self) -> bool {
+        !lock(&self.health).unhealthy
+    }
+
+    /// Record a served request.
+    fn record_success(&self) {
+        self.requests.fetch_add(1, Ordering::Relaxed);
+        let recovered = {
+            let mut health = lock(&self.health);
+            health.consecutive_failures = 0;
+            std::mem::replace(&mut health.unhealthy, false)
+        };
+        if recovered {
+            info!(endpoint = %self.name, "RPC endpoint recovered");
+        }
+    }
+
+    /// Record a failed request and take the endpoint out of rotation.
+    fn record_failure(&self, error: &ProviderError) {
+        self.failures.fetch_add(1, Ordering::Relaxed);
+        let was_healthy = {
+            let mut health = lock(&self.health);
+            health.consecutive_failures = health.consecutive_failures.saturating_add(1);
+            health.last_error = Some(error.to_string());
+            !std::mem::replace(&mut health.unhealthy, true)
+        };
+        if was_healthy {
+            warn!(endpoint = %self.name, error = %error, "RPC endpoint marked unhealthy");
+        }
+    }
+
+    /// Snapshot of the endpoint's health.
+    fn health(&self) -> EndpointHealth {
+        let health = lock(&self.health);
+        EndpointHealth {
+            name: self.name.clone(),
+            healthy: !health.unhealthy,
+            consecutive_failures: health.consecutive_failures,
+            requests: self.requests.load(Ordering::Relaxed),
+            failures: self.failures.load(Ordering::Relaxed),
+            last_error: health.last_error.clone(),
+        }
+    }
+}
+
+/// Lock a mutex, recovering the data if a holder panicked.
+///
+/// Health bookkeeping stays usable even if it was left half-updated.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Whether another endpoint might serve a request that failed with `error`.
+///
+/// Timeouts waiting for a receipt say nothing about the endpoint, and
+/// non-retryable errors (reverts, bad input) would fail everywhere.
+const fn is_endpoint_failure(error: &ProviderError) -> bool {
+    error.is_retryable() && !matches!(error, ProviderError::Timeout(_))
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// FAILOVER PROVIDER
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Chain provider that fails over across an ordered list of endpoints.
+///
+/// Requests go to the first healthy endpoint in the list. An endpoint that
+/// fails with a connection error, a rate limit or a server error is marked
+/// unhealthy and the request is retried on the next one; other errors are
+/// returned as they are. If every endpoint is unhealthy, all of them are
+/// tried in order rather than failing outright.
+///
+/// See the [module documentation](self) for health probes and sticky
+/// routing of nonce-sensitive requests.
+#[derive(Debug)]
+pub struct FailoverProvider<P> {
+    /// Endpoints, most preferred first.
+    endpoints: Vec<Endpoint<P>>,
+
+    /// Chain ID all endpoints serve.
+    chain_id: u64,
+
+    /// Endpoint that last served a nonce-sensitive request, and when.
+    pinned: Mutex<Option<(usize, Instant)>>,
+
+    /// How long nonce-sensitive requests stick to the pinned endpoint.
+    sticky_window: Duration,
+}
+
+impl<P: ChainProvider> FailoverProvider<P> {
+    /// Create a failover provider over named endpoints, most preferred first.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProviderError::InvalidConfig`] if no endpoints are given or
+    /// they do not all serve the same chain.
+    pub fn new(endpoints: Vec<(String, P)>) -> Result<Self> {
+        let Some((first_name, first)) = endpoints.first() else {
+            return Err(ProviderError::InvalidConfig(
+                "failover provider needs at least one endpoint".into(),
+            ));
+        };
+        let chain_id = first.chain_id();
+        if let Some((name, provider)) = endpoints.iter().find(|(_, p)| p.chain_id() != chain_id) {
+            return Err(ProviderError::InvalidConfig(format!(
+                "endpoint {name} serves chain {}, but {first_name} serves chain {chain_id}",
+                provider.chain_id()
+            )));
+        }
+
+        Ok(Self {
+            endpoints: endpoints
+                .into_iter()
+                .map(|(name, provider)| Endpoint {
+                    name,
+                    provider,
+                    health: Mutex::new(HealthState::default()),
+                    requests: AtomicU64::new(0),
+                    failures: AtomicU64::new(0),
+                })
+                .collect(),
+            chain_id,
+            pinned: Mutex::new(None),
+            sticky_window: DEFAULT_STICKY_WINDOW,
+        })
+    }
+
+    /// Set how long nonce-sensitive requests stick to the same endpoint.
+    ///
+    /// Default is 30 seconds. Zero disables sticky routing.
+    #[must_use]
+    pub const fn with_sticky_window(mut self, window: Duration) -> Self {
+        self.sticky_window = window;
+        self
+    }
+
+    /// Name of the endpoint the next request goes to.
+    #[must_use]
+    pub fn current_endpoint(&self) -> &str {
+        let index = self.route(false).first().copied().unwrap_or_default();
+        &self.endpoints[index].name
+    }
+
+    /// Health of every endpoint, most preferred first.
+    #[must_use]
+    pub fn endpoint_health(&self) -> Vec<EndpointHealth> {
+        self.endpoints.iter().map(Endpoint::health).collect()
+    }
+
+    /// Get the number of endpoints requests are routed to.
+    #[must_use]
+    pub fn healthy_count(&self) -> usize {
+        self.endpoints.iter().filter(|e| e.is_healthy()).count()
+    }
+
+    /// Probe every unhealthy endpoint and restore those that answer with the
+    /// right chain ID.
+    ///
+    /// # Returns
+    ///
+    /// The number of endpoints restored.
+    pub async fn probe_unhealthy(&self) -> usize {
+        let mut restored = 0;
+        for endpoint in self.endpoints.iter().filter(|e| !e.is_healthy()) {
+            match endpoint.provider.get_chain_id().await {
+                Ok(chain_id) if chain_id == self.chain_id => {
+                    endpoint.record_success();
+                    restored += 1;
+                }
+                Ok(chain_id) => {
+                    let error = ProviderError::InvalidConfig(format!(
+                        "endpoint serves chain {chain_id}, expected {}",
+                        self.chain_id
+                    ));
+                    warn!(endpoint = %endpoint.name, error = %error, "Health probe failed");
+                    endpoint.record_failure(&error);
+                }
+                Err(e) => {
+                    debug!(endpoint = %endpoint.name, error = %e, "Health probe failed");
+                    endpoint.record_failure(&e);
+                }
+            }
+        }
+        restored
+    }
+
+    /// Run [`probe_unhealthy`](Self::probe_unhealthy) every `interval` in a
+    /// background task.
+    ///
+    /// The task ends on its own once the provider is dropped.
+    pub fn spawn_health_probes(self: &Arc<Self>, interval: Duration) -> JoinHandle<()> {
+        let provider: Weak<Self> = Arc::downgrade(self);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                let Some(provider) = provider.upgrade() else {
+                    break;
+                };
+                provider.probe_unhealthy().await;
+            }
+        })
+    }
+
+    /// Order in which endpoints are tried: healthy ones by preference, then
+    /// unhealthy ones as a last resort. Nonce-sensitive requests try the
+    /// pinned endpoint first while it is healthy and the pin is fresh.
+    fn route(&self, sticky: bool) -> Vec<usize> {
+        let (healthy, unhealthy): (Vec<usize>, Vec<usize>) =
+            (0..self.endpoints.len()).partition(|&i| self.endpoints[i].is_healthy());
+        let mut order = healthy;
+        if sticky
+            && let Some((pinned, at)) = *lock(&self.pinned)
+            && at.elapsed() < self.sticky_window
+            && let Some(position) = order.iter().position(|&i| i == pinned)
+        {
+            order[..=position].rotate_right(1);
+        }
+        order.extend(unhealthy);
+        order
+    }
+
+    /// Run `request` against endpoints in routing order until one serves it.
+    ///
+    /// Stops at the first error that another endpoint could not avoid.
+    async fn request<'a, T>(
+        &'a self,
+        method: &'static str,
+        sticky: bool,
+        request: impl Fn(&'a P) -> BoxFuture<'a, Result<T>>,
+    ) -> Result<T> {
+        self.request_among(method, sticky, |_| true, request).await
+    }
+
+    /// Like [`request`](Self::request), but only on endpoints that `supports`.
+    async fn request_among<'a, T>(
+        &'a self,
+        method: &'static str,
+        sticky: bool,
+        supports: impl Fn(&P) -> bool,
+        request: impl Fn(&'a P) -> BoxFuture<'a, Result<T>>,
+    ) -> Result<T> {
+        let mut last_error = None;
+        for index in self.route(sticky) {
+            let endpoint = &self.endpoints[index];
+            if !supports(&endpoint.provider) {
+                continue;
+            }
+            match request(&endpoint.provider).await {
+                Ok(value) => {
+                    endpoint.record_success();
+                    if sticky {
+                        *lock(&self.pinned) = Some((index, Instant::now()));
+                    }
+                    return Ok(value);
+                }
+                Err(e) if is_endpoint_failure(&e) => {
+                    debug!(endpoint = %endpoint.name, method, error = %e, "Failing over");
+                    endpoint.record_failure(&e);
+                    last_error = Some(e);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        Err(last_error.unwrap_or_else(|| ProviderError::unsupported(method)))
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// CHAIN PROVIDER IMPLEMENTATION
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[async_trait]
+impl<P: ChainProvider> ChainProvider for FailoverProvider<P> {
+    fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    async fn get_chain_id(&self) -> Result<u64> {
+        self.request("get_chain_id", false, |p| p.get_chain_id())
+            .await
+    }
+
+    async fn get_balance(&self, address: Address) -> Result<U256> {
+        self.request("get_balance", false, |p| p.get_balance(address))
+            .await
+    }
+
+    async fn get_nonce(&self, address: Address) -> Result<u64> {
+        self.request("get_nonce", true, |p| p.get_nonce(address))
+            .await
+    }
+
+    async fn get_pending_nonce(&self, address: Address) -> Result<u64> {
+        self.request("get_pending_nonce", true, |p| p.get_pending_nonce(address))
+            .await
+    }
+
+    async fn send_raw_transaction(&self, tx: Bytes) -> Result<TxHash> {
+        self.request("send_raw_transaction", true, |p| {
+            p.send_raw_transaction(tx.clone())
+        })
+        .await
+    }
+
+    async fn wait_for_receipt(
+        &self,
+        tx_hash: TxHash,
+        timeout: Duration,
+    ) -> Result<TransactionReceipt> {
+        self.request("wait_for_receipt", true, |p| {
+            p.wait_for_receipt(tx_hash, timeout)
+        })
+        .await
+    }
+
+    async fn estimate_gas(&self, tx: &TransactionRequest) -> Result<u64> {
+        self.request("estimate_gas", false, |p| {
+            let tx = tx.clone();
+            Box::pin(async move { p.estimate_gas(&tx).await })
+        })
+        .await
+    }
+
+    async fn gas_price(&self) -> Result<u128> {
+        self.request("gas_price", false, |p| p.gas_price()).await
+    }
+
+    async fn get_block_number(&self) -> Result<u64> {
+        self.request("get_block_number", false, |p| p.get_block_number())
+            .await
+    }
+
+    async fn call(&self, tx: &TransactionRequest) -> Result<Bytes> {
+        self.request("call", false, |p| {
+            let tx = tx.clone();
+            Box::pin(async move { p.call(&tx).await })
+        })
+        .await
+    }
+
+    async fn get_token_balance(&self, token: Address, account: Address) -> Result<U256> {
+        self.request("get_token_balance", false, |p| {
+            p.get_token_balance(token, account)
+        })
+        .await
+    }
+
+    fn supports_multicall(&self) -> bool {
+        self.endpoints
+            .iter()
+            .all(|e| e.provider.supports_multicall())
+    }
+
+    async fn get_token_balances(
+        &self,
+        queries: &[(Address, Address)],
+    ) -> Result<Vec<Option<U256>>> {
+        self.request("get_token_balances", false, |p| {
+            let queries = queries.to_vec();
+            Box::pin(async move { p.get_token_balances(&queries).await })
+        })
+        .await
+    }
+}
+
+/// Extended methods are only sent to endpoints that support them.
+#[async_trait]
+impl<P: ExtendedChainProvider> ExtendedChainProvider for FailoverProvider<P> {
+    fn supports_realtime(&self) -> bool {
+        self.endpoints
+            .iter()
+            .any(|e| e.provider.supports_realtime())
+    }
+
+    fn supports_cursor_pagination(&self) -> bool {
+        self.endpoints
+            .iter()
+            .any(|e| e.provider.supports_cursor_pagination())
+    }
+
+    async fn send_realtime(&self, tx: Bytes) -> Result<TransactionReceipt> {
+        if !self.supports_realtime() {
+            let hash = self.send_raw_transaction(tx).await?;
+            return self.wait_for_receipt(hash, Duration::from_secs(30)).await;
+        }
+        self.request_among("send_realtime", true, P::supports_realtime, |p| {
+            p.send_realtime(tx.clone())
+        })
+        .await
+    }
+
+    async fn get_logs_with_cursor(
+        &self,
+        filter: &LogFilter,
+        cursor: Option<&str>,
+    ) -> Result<LogsPage> {
+        // Cursors are opaque to other endpoints, so a page stays on the endpoint
+        // that issued its cursor only as long as that endpoint stays healthy
+        self.request_among(
+            "get_logs_with_cursor",
+            false,
+            P::supports_cursor_pagination,
+            |p| {
+                let filter = filter.clone();
+                let cursor = cursor.map(str::to_owned);
+                Box::pin(async move { p.get_logs_with_cursor(&filter, cursor.as_deref()).await })
+            },
+        )
+        .await
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockProvider;
+
+    fn failover(providers: Vec<(&str, Arc<MockProvider>)>) -> FailoverProvider<Arc<MockProvider>> {
+        FailoverProvider::new(
+            providers
+                .into_iter()
+                .map(|(name, provider)| (name.to_string(), provider))
+                .collect(),
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn fails_over_to_healthy_endpoint() {
+        let dead = Arc::new(MockProvider::new());
+        let healthy = Arc::new(MockProvider::new());
+        dead.set_offline(true);
+        healthy.set_balance(Address::ZERO, U256::from(7));
+        let provider = failover(vec![("dead", Arc::clone(&dead)), ("healthy", healthy)]);
+
+        assert_eq!(provider.current_endpoint(), "dead");
+        assert_eq!(
+            provider.get_balance(Address::ZERO).await.unwrap(),
+            U256::from(7)
+        );
+        assert_eq!(provider.current_endpoint(), "healthy");
+
+        // The dead endpoint is skipped from now on
+        provider.get_block_number().await.unwrap();
+        let health = provider.endpoint_health();
+        assert!(!health[0].healthy);
+        assert_eq!(health[0].failures, 1);
+        assert!(health[0].last_error.is_some());
+        assert_eq!(health[1].requests, 2);
+        assert_eq!(provider.healthy_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn probes_restore_recovered_endpoint() {
+        let primary = Arc::new(MockProvider::new());
+        let backup = Arc::new(MockProvider::new());
+        let provider = failover(vec![("primary", Arc::clone(&primary)), ("backup", backup)]);
+
+        primary.set_offline(true);
+        provider.get_block_number().await.unwrap();
+        assert_eq!(provider.current_endpoint(), "backup");
+
+        // Still down: the probe keeps it out of rotation
+        assert_eq!(provider.probe_unhealthy().await, 0);
+        assert_eq!(provider.endpoint_health()[0].consecutive_failures, 2);
+
+        primary.set_offline(false);
+        assert_eq!(provider.probe_unhealthy().await, 1);
+        assert_eq!(provider.current_endpoint(), "primary");
+        assert_eq!(provider.endpoint_health()[0].consecutive_failures, 0);
+    }
+
+    #[tokio::test]
+    async fn nonce_requests_stick_to_endpoint() {
+        let primary = Arc::new(MockProvider::new());
+        let backup = Arc::new(MockProvider::new());
+        backup.set_nonce(Address::ZERO, 5);
+        let provider = failover(vec![("primary", Arc::clone(&primary)), ("backup", backup)]);
+
+        primary.set_offline(true);
+        assert_eq!(provider.get_nonce(Address::ZERO).await.unwrap(), 5);
+        primary.set_offline(false);
+        provider.probe_unhealthy().await;
+
+        // Plain reads go back to the primary, nonce reads stay on the backup
+        provider.get_balance(Address::ZERO).await.unwrap();
+        assert_eq!(provider.get_nonce(Address::ZERO).await.unwrap(), 5);
+        let health = provider.endpoint_health();
+        assert_eq!((health[0].requests, health[1].requests), (2, 2));
+
+        // Without a sticky window they follow the preference order again
+        let provider = provider.with_sticky_window(Duration::ZERO);
+        assert_eq!(provider.get_nonce(Address::ZERO).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn request_errors_do_not_fail_over() {
+        let primary = Arc::new(MockProvider::new());
+        let backup = Arc::new(MockProvider::new());
+        primary.fail_token_balance(Address::ZERO, Address::ZERO);
+        let provider = failover(vec![("primary", primary), ("backup", Arc::clone(&backup))]);
+
+        // A reverting read would revert on every endpoint
+        let err = provider
+            .get_token_balance(Address::ZERO, Address::ZERO)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ProviderError::InvalidResponse(_)));
+        assert_eq!(provider.healthy_count(), 2);
+        assert_eq!(provider.endpoint_health()[1].requests, 0);
+        assert!(!is_endpoint_failure(&ProviderError::Timeout(
+            Duration::from_secs(1)
+        )));
+    }
+
+    #[tokio::test]
+    async fn all_endpoints_down_returns_last_error() {
+        let a = Arc::new(MockProvider::new());
+        let b = Arc::new(MockProvider::new());
+        a.set_offline(true);
+        b.set_offline(true);
+        let provider = failover(vec![("a", a), ("b", Arc::clone(&b))]);
+
+        let err = provider.gas_price().await.unwrap_err();
+        assert!(matches!(err, ProviderError::Connection(_)));
+        assert_eq!(provider.healthy_count(), 0);
+
+        // Unhealthy endpoints are still tried as a last resort
+        b.set_offline(false);
+        assert!(provider.gas_price().await.is_ok());
+        assert_eq!(provider.current_endpoint(), "b");
+    }
+
+    #[test]
+    fn rejects_chain_id_mismatch() {
+        let err = FailoverProvider::new(vec![
+            ("testnet".to_string(), MockProvider::with_chain_id(6343)),
+            ("anvil".to_string(), MockProvider::with_chain_id(31337)),
+        ])
+        .unwrap_err();
+        assert!(matches!(err, ProviderError::InvalidConfig(_)));
+        assert!(err.to_string().contains("anvil"));
+
+        let empty: Result<FailoverProvider<MockProvider>> = FailoverProvider::new(vec![]);
+        assert!(matches!(empty, Err(ProviderError::InvalidConfig(_))));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn background_probes_restore_endpoints() {
+        let primary = Arc::new(MockProvider::new());
+        let provider = Arc::new(failover(vec![
+            ("primary", Arc::clone(&primary)),
+            ("backup", Arc::new(MockProvider::new())),
+        ]));
+        primary.set_offline(true);
+        provider.get_block_number().await.unwrap();
+        primary.set_offline(false);
+
+        let probes = provider.spawn_health_probes(Duration::from_secs(10));
+        tokio::time::sleep(Duration::from_secs(11)).await;
+        assert_eq!(provider.current_endpoint(), "primary");
+
+        // Dropping the provider ends the task
+        drop(provider);
+        tokio::time::sleep(Duration::from_secs(11)).await;
+        assert!(probes.is_finished());
+    }
+}
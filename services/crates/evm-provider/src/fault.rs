@@ -0,0 +1,623 @@
+//! Fault injection for the mock providers.
+//!
+//! A [`FaultPlan`] says how a mock endpoint misbehaves: which requests fail
+//! and with what error, which sent transactions revert, and how long
+//! responses take. Installed on a [`MockProvider`](crate::mock::MockProvider)
+//! (or the `test-utils` `MockChainProvider`), it is consulted on every
+//! request and counts what it served, so tests can reconcile their own
+//! bookkeeping with what the endpoint saw.
+//!
+//! Rules apply in the order they were added; the first that fires decides
+//! the request's fault. Chance-based rules draw from a sequence seeded by the
+//! plan's seed, so the same requests always meet the same faults.
+//!
+//! # Example
+//!
+//! ```
+//! use std::time::Duration;
+//!
+//! use evm_provider::fault::{Fault, FaultPlan, Latency, Method};
+//!
+//! let plan = FaultPlan::new(7)
+//!     // The next three sends time out, the ones after go through
+//!     .fail_next(Method::SendRawTransaction, 3, Fault::Timeout)
+//!     // One request in twenty times out
+//!     .flaky(0.05, Fault::Timeout)
+//!     // Sent extracts are mined, but revert
+//!     .revert_matching(|raw| raw.starts_with(b"extract"))
+//!     .latency(
+//!         Method::WaitForReceipt,
+//!         Latency::Uniform(Duration::from_millis(5), Duration::from_millis(50)),
+//!     );
+//! assert_eq!(plan.calls(Method::SendRawTransaction), 0);
+//! ```
+
+#![allow(clippy::expect_used)]
+#![allow(clippy::missing_panics_doc)]
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+use alloy::primitives::Bytes;
+
+use crate::error::ProviderError;
+use crate::mock::splitmix64;
+
+/// Duration reported by injected timeouts.
+const INJECTED_TIMEOUT: Duration = Duration::from_secs(30);
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// METHODS AND FAULTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// A [`ChainProvider`](crate::ChainProvider) method, for scripting and
+/// injection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Method {
+    /// `get_chain_id`.
+    ChainId,
+    /// `get_balance`.
+    Balance,
+    /// `get_nonce` and `get_pending_nonce`.
+    Nonce,
+    /// `get_token_balance` and `get_token_balances`.
+    TokenBalance,
+    /// `send_raw_transaction`.
+    SendRawTransaction,
+    /// `send_realtime` of [`ExtendedChainProvider`](crate::ExtendedChainProvider).
+    SendRealtime,
+    /// `wait_for_receipt`.
+    WaitForReceipt,
+    /// `estimate_gas`.
+    EstimateGas,
+    /// `gas_price`.
+    GasPrice,
+    /// `get_block_number`.
+    BlockNumber,
+    /// `call`.
+    Call,
+}
+
+impl Method {
+    /// Check whether the method sends a transaction.
+    #[must_use]
+    pub const fn is_send(self) -> bool {
+        matches!(self, Self::SendRawTransaction | Self::SendRealtime)
+    }
+}
+
+/// How an injected fault shows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Fault {
+    /// The request times out.
+    Timeout,
+    /// The endpoint cannot be reached.
+    Connection,
+    /// The endpoint rejects the request with HTTP 429.
+    RateLimited,
+    /// A sent transaction is mined, but reverts; any other request fails
+    /// with an "execution reverted" RPC error.
+    Revert,
+}
+
+impl Fault {
+    /// The error a request to `method` fails with, `None` for a sent
+    /// transaction that reverts once mined.
+    fn error(self, method: Method) -> Option<ProviderError> {
+        match self {
+            Self::Timeout => Some(ProviderError::Timeout(INJECTED_TIMEOUT)),
+            Self::Connection => Some(ProviderError::Connection(
+                "injected fault: connection refused".into(),
+            )),
+            Self::RateLimited => Some(ProviderError::RateLimited(
+                "injected fault: HTTP 429".into(),
+            )),
+            Self::Revert if method.is_send() => None,
+            Self::Revert => Some(ProviderError::rpc(3, "execution reverted: injected fault")),
+        }
+    }
+}
+
+/// Distribution of the time a response takes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Latency {
+    /// Always this long.
+    Fixed(Duration),
+    /// Uniformly between the two, inclusive.
+    Uniform(Duration, Duration),
+    /// `base` usually, `spike` for `permille` in a thousand responses.
+    Spikes {
+        /// Usual latency.
+        base: Duration,
+        /// Latency of a spike.
+        spike: Duration,
+        /// Spikes per thousand responses.
+        permille: u32,
+    },
+}
+
+impl Latency {
+    /// Draw a latency from two uniform samples.
+    fn sample(self, a: u64, b: u64) -> Duration {
+        match self {
+            Self::Fixed(latency) => latency,
+            Self::Uniform(min, max) => {
+                let millis = |d: Duration| u64::try_from(d.as_millis()).unwrap_or(u64::MAX);
+                let (min, max) = (millis(min), millis(max));
+                let span = max.saturating_sub(min).saturating_add(1);
+                Duration::from_millis(min + a % span)
+            }
+            Self::Spikes {
+                base,
+                spike,
+                permille,
+            } => {
+                if b % 1000 < u64::from(permille) {
+                    spike
+                } else {
+                    base
+                }
+            }
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// RULES
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Which requests a rule hits.
+enum When {
+    /// The next `n` requests.
+    Next(u32),
+    /// Each request with this chance (0.0 - 1.0).
+    Rate(f64),
+    /// Every `period`th request, starting with the `period`th.
+    Every { period: u64, seen: u64 },
+    /// Requests made while the plan's clock is in `[from, until)`.
+    Window { from: SystemTime, until: SystemTime },
+    /// Sent transactions whose raw bytes match.
+    Matching(Box<dyn Fn(&Bytes) -> bool + Send + Sync>),
+}
+
+impl fmt::Debug for When {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Next(n) => f.debug_tuple("Next").field(n).finish(),
+            Self::Rate(rate) => f.debug_tuple("Rate").field(rate).finish(),
+            Self::Every { period, seen } => f
+                .debug_struct("Every")
+                .field("period", period)
+                .field("seen", seen)
+                .finish(),
+            Self::Window { from, until } => f
+                .debug_struct("Window")
+                .field("from", from)
+                .field("until", until)
+                .finish(),
+            Self::Matching(_) => f.write_str("Matching(..)"),
+        }
+    }
+}
+
+/// A fault and the requests it hits.
+#[derive(Debug)]
+struct Rule {
+    /// Method the rule applies to, `None` for all of them.
+    method: Option<Method>,
+    when: When,
+    fault: Fault,
+}
+
+/// What the plan does to one request.
+#[derive(Debug, Default)]
+pub(crate) struct Injection {
+    /// Error to fail the request with.
+    pub error: Option<ProviderError>,
+    /// Whether the sent transaction reverts once mined.
+    pub revert: bool,
+    /// Time to wait before responding.
+    pub latency: Duration,
+}
+
+/// Rules and counters, behind one lock.
+#[derive(Debug, Default)]
+struct State {
+    rules: Vec<Rule>,
+    latency: HashMap<Method, Latency>,
+    /// Random samples drawn so far.
+    draws: u64,
+    calls: HashMap<Method, u64>,
+    faults: HashMap<Method, u64>,
+}
+
+impl State {
+    /// Next sample of the plan's random sequence.
+    const fn draw(&mut self, seed: u64) -> u64 {
+        self.draws += 1;
+        splitmix64(seed ^ splitmix64(self.draws))
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// FAULT PLAN
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// How a mock endpoint misbehaves, and what it served so far.
+///
+/// Built up with the rule methods, then shared with the mock in an `Arc`
+/// so the test can still read the counters.
+pub struct FaultPlan {
+    /// Seed of the random sequence.
+    seed: u64,
+
+    /// Time [windows](Self::outage) are measured on.
+    clock: Box<dyn Fn() -> SystemTime + Send + Sync>,
+
+    /// Rules and counters.
+    state: Mutex<State>,
+}
+
+impl fmt::Debug for FaultPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FaultPlan")
+            .field("seed", &self.seed)
+            .field("state", &self.state)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for FaultPlan {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl FaultPlan {
+    /// A plan without faults, drawing chances from `seed`.
+    #[must_use]
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            clock: Box::new(SystemTime::now),
+            state: Mutex::default(),
+        }
+    }
+
+    /// Measure [outages](Self::outage) on `clock` instead of the system
+    /// clock, e.g. a test's virtual clock.
+    #[must_use]
+    pub fn with_clock(mut self, clock: impl Fn() -> SystemTime + Send + Sync + 'static) -> Self {
+        self.clock = Box::new(clock);
+        self
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().expect("lock poisoned")
+    }
+
+    fn rule(self, method: Option<Method>, when: When, fault: Fault) -> Self {
+        self.state().rules.push(Rule {
+            method,
+            when,
+            fault,
+        });
+        self
+    }
+
+    // ───────────────────────────────────────────────────────────────────────────
+    // Rules
+    // ───────────────────────────────────────────────────────────────────────────
+
+    /// Fail the next `count` requests to `method`, then let them through.
+    #[must_use]
+    pub fn fail_next(self, method: Method, count: u32, fault: Fault) -> Self {
+        self.rule(Some(method), When::Next(count), fault)
+    }
+
+    /// Fail each request to `method` with chance `rate` (0.0 - 1.0).
+    #[must_use]
+    pub fn fail_rate(self, method: Method, rate: f64, fault: Fault) -> Self {
+        self.rule(Some(method), When::Rate(rate), fault)
+    }
+
+    /// Fail each request, to any method, with chance `rate` (0.0 - 1.0).
+    #[must_use]
+    pub fn flaky(self, rate: f64, fault: Fault) -> Self {
+        self.rule(None, When::Rate(rate), fault)
+    }
+
+    /// Fail every `period`th request to `method`; a period of 2 alternates
+    /// success and failure.
+    #[must_use]
+    pub fn fail_every(self, method: Method, period: u64, fault: Fault) -> Self {
+        let when = When::Every {
+            period: period.max(1),
+            seen: 0,
+        };
+        self.rule(Some(method), when, fault)
+    }
+
+    /// Fail every request made from `from` until `until` with a connection
+    /// error, as a dead endpoint would.
+    #[must_use]
+    pub fn outage(self, from: SystemTime, until: SystemTime) -> Self {
+        self.rule(None, When::Window { from, until }, Fault::Connection)
+    }
+
+    /// Fail the requests to `method` made from `from` until `until` with a
+    /// connection error, as an endpoint that lost only part of its backend
+    /// would.
+    #[must_use]
+    pub fn outage_of(self, method: Method, from: SystemTime, until: SystemTime) -> Self {
+        let when = When::Window { from, until };
+        self.rule(Some(method), when, Fault::Connection)
+    }
+
+    /// Make sent transactions whose raw bytes match `predicate` revert once
+    /// mined, however they are sent.
+    #[must_use]
+    pub fn revert_matching(
+        self,
+        predicate: impl Fn(&Bytes) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        // Only sends come with raw bytes to match
+        let when = When::Matching(Box::new(predicate));
+        self.rule(None, when, Fault::Revert)
+    }
+
+    /// Delay responses of `method` by a latency drawn from `latency`.
+    #[must_use]
+    pub fn latency(self, method: Method, latency: Latency) -> Self {
+        self.state().latency.insert(method, latency);
+        self
+    }
+
+    // ───────────────────────────────────────────────────────────────────────────
+    // Counters
+    // ───────────────────────────────────────────────────────────────────────────
+
+    /// Requests to `method` served so far, faulty or not.
+    #[must_use]
+    pub fn calls(&self, method: Method) -> u64 {
+        self.state().calls.get(&method).copied().unwrap_or_default()
+    }
+
+    /// Requests to `method` that met a fault so far, reverted sends
+    /// included.
+    #[must_use]
+    pub fn faults(&self, method: Method) -> u64 {
+        self.state()
+            .faults
+            .get(&method)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Requests to any method that met a fault so far.
+    #[must_use]
+    pub fn total_faults(&self) -> u64 {
+        self.state().faults.values().sum()
+    }
+
+    // ───────────────────────────────────────────────────────────────────────────
+    // Injection
+    // ───────────────────────────────────────────────────────────────────────────
+
+    /// Decide what happens to a request to `method`, counting it; `tx` is
+    /// the raw transaction of a send.
+    pub(crate) fn inject(&self, method: Method, tx: Option<&Bytes>) -> Injection {
+        let now = (self.clock)();
+        let mut state = self.state();
+        *state.calls.entry(method).or_default() += 1;
+
+        let mut fault = None;
+        for index in 0..state.rules.len() {
+            let rule = &state.rules[index];
+            if rule.method.is_some_and(|m| m != method) {
+                continue;
+            }
+            let fires = match &rule.when {
+                When::Next(0) => false,
+                When::Rate(rate) => {
+                    let rate = *rate;
+                    let sample = state.draw(self.seed) >> 11;
+                    #[allow(clippy::cast_precision_loss)]
+                    let fraction = sample as f64 / (1_u64 << 53) as f64;
+                    fraction < rate
+                }
+                When::Window { from, until } => (*from..*until).contains(&now),
+                When::Matching(predicate) => tx.is_some_and(predicate),
+                When::Next(_) | When::Every { .. } => true,
+            };
+            let rule = &mut state.rules[index];
+            let fires = match &mut rule.when {
+                When::Next(remaining) if fires => {
+                    *remaining -= 1;
+                    true
+                }
+                When::Every { period, seen } => {
+                    *seen += 1;
+                    *seen % *period == 0
+                }
+                _ => fires,
+            };
+            if fires {
+                fault = Some(rule.fault);
+                break;
+            }
+        }
+
+        let latency = state.latency.get(&method).copied();
+        let latency = latency.map_or(Duration::ZERO, |latency| {
+            let (a, b) = (state.draw(self.seed), state.draw(self.seed));
+            latency.sample(a, b)
+        });
+        if fault.is_some() {
+            *state.faults.entry(method).or_default() += 1;
+        }
+        drop(state);
+
+        Injection {
+            error: fault.and_then(|fault| fault.error(method)),
+            revert: fault == Some(Fault::Revert) && method.is_send(),
+            latency,
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    use super::*;
+
+    /// Which of `n` requests to `method` fail.
+    fn failures(plan: &FaultPlan, method: Method, n: usize) -> Vec<bool> {
+        (0..n)
+            .map(|_| plan.inject(method, None).error.is_some())
+            .collect()
+    }
+
+    #[test]
+    fn fails_next_then_succeeds() {
+        let plan = FaultPlan::new(0).fail_next(Method::SendRawTransaction, 3, Fault::Timeout);
+
+        assert_eq!(
+            failures(&plan, Method::SendRawTransaction, 5),
+            [true, true, true, false, false]
+        );
+        assert!(plan.inject(Method::Balance, None).error.is_none());
+        assert_eq!(plan.calls(Method::SendRawTransaction), 5);
+        assert_eq!(plan.faults(Method::SendRawTransaction), 3);
+        assert_eq!(plan.total_faults(), 3);
+    }
+
+    #[test]
+    fn faults_map_to_provider_errors() {
+        let error = |fault| {
+            FaultPlan::new(0)
+                .fail_next(Method::Call, 1, fault)
+                .inject(Method::Call, None)
+                .error
+        };
+
+        assert!(matches!(
+            error(Fault::Timeout),
+            Some(ProviderError::Timeout(_))
+        ));
+        assert!(error(Fault::Connection).is_some_and(|e| e.is_retryable()));
+        assert!(error(Fault::RateLimited).is_some_and(|e| e.is_rate_limited()));
+        assert!(error(Fault::Revert).is_some_and(|e| e.revert_reason().is_some()));
+    }
+
+    #[test]
+    fn alternates_and_rates() {
+        let plan = FaultPlan::new(0).fail_every(Method::Nonce, 2, Fault::Connection);
+        assert_eq!(
+            failures(&plan, Method::Nonce, 4),
+            [false, true, false, true]
+        );
+
+        let plan = FaultPlan::new(3).flaky(0.25, Fault::Timeout);
+        let failed = failures(&plan, Method::GasPrice, 4000);
+        let count = failed.iter().filter(|&&f| f).count();
+        assert!((800..1200).contains(&count), "{count} failures");
+        // The same seed fails the same requests
+        let again = FaultPlan::new(3).flaky(0.25, Fault::Timeout);
+        assert_eq!(failures(&again, Method::GasPrice, 4000), failed);
+    }
+
+    #[test]
+    fn outages_follow_the_clock() {
+        let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let now = Arc::new(AtomicU64::new(0));
+        let elapsed = Arc::clone(&now);
+        let plan = FaultPlan::new(0)
+            .outage(
+                start + Duration::from_secs(60),
+                start + Duration::from_secs(660),
+            )
+            .with_clock(move || start + Duration::from_secs(elapsed.load(Ordering::Relaxed)));
+
+        let fails_at = |secs| {
+            now.store(secs, Ordering::Relaxed);
+            plan.inject(Method::BlockNumber, None).error.is_some()
+        };
+        assert!(!fails_at(59));
+        assert!(fails_at(60));
+        assert!(fails_at(659));
+        assert!(!fails_at(660));
+    }
+
+    #[test]
+    fn outages_of_one_method() {
+        let start = SystemTime::UNIX_EPOCH;
+        let plan = FaultPlan::new(0)
+            .outage_of(
+                Method::SendRawTransaction,
+                start,
+                start + Duration::from_secs(60),
+            )
+            .with_clock(move || start);
+
+        let send = plan.inject(Method::SendRawTransaction, Some(&Bytes::new()));
+        assert!(matches!(send.error, Some(ProviderError::Connection(_))));
+        assert!(plan.inject(Method::BlockNumber, None).error.is_none());
+        assert_eq!(plan.faults(Method::SendRawTransaction), 1);
+    }
+
+    #[test]
+    fn reverts_matching_sends() {
+        let plan = FaultPlan::new(0).revert_matching(|raw| raw.starts_with(b"extract"));
+
+        let extract = plan.inject(
+            Method::SendRawTransaction,
+            Some(&Bytes::from_static(b"extract 1")),
+        );
+        assert!(extract.revert && extract.error.is_none());
+        let other = plan.inject(
+            Method::SendRawTransaction,
+            Some(&Bytes::from_static(b"jack_in")),
+        );
+        assert!(!other.revert);
+        assert_eq!(plan.faults(Method::SendRawTransaction), 1);
+    }
+
+    #[test]
+    fn latency_distributions() {
+        let base = Duration::from_millis(10);
+        let plan = FaultPlan::new(0)
+            .latency(Method::Call, Latency::Fixed(base))
+            .latency(
+                Method::GasPrice,
+                Latency::Uniform(Duration::from_millis(5), Duration::from_millis(9)),
+            )
+            .latency(
+                Method::Balance,
+                Latency::Spikes {
+                    base,
+                    spike: Duration::from_secs(2),
+                    permille: 500,
+                },
+            );
+
+        assert_eq!(plan.inject(Method::Call, None).latency, base);
+        for _ in 0..50 {
+            let latency = plan.inject(Method::GasPrice, None).latency;
+            assert!((5..=9).contains(&latency.as_millis()), "{latency:?}");
+        }
+        let spikes = (0..200)
+            .filter(|_| plan.inject(Method::Balance, None).latency > base)
+            .count();
+        assert!((50..150).contains(&spikes), "{spikes} spikes");
+        assert_eq!(plan.inject(Method::Nonce, None).latency, Duration::ZERO);
+    }
+}
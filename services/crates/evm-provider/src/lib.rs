@@ -32,7 +32,8 @@
 //!
 //! **This crate provides:**
 //! - Traits for chain-agnostic code (`ChainProvider`, `ExtendedChainProvider`)
-//! - Thread-safe nonce management (`NonceManager`, `LocalNonceManager`)
+//! - Thread-safe nonce management (`NonceManager`, `LocalNonceManager`) and
+//!   pending transaction tracking (`PendingTracker`)
 //! - Provider implementations for different chains
 //!
 //! **Use this crate when:**
@@ -75,7 +76,14 @@
 //!
 //! - [`traits`] - Core [`ChainProvider`] and [`ExtendedChainProvider`] traits
 //! - [`types`] - Transaction requests, receipts, and log filters
+//! - [`signer`] - Transaction signing via [`TransactionSigner`], in process
+//!   ([`LocalSigner`]) or by a remote signer ([`RemoteHttpSigner`])
 //! - [`nonce`] - Thread-safe nonce management via [`LocalNonceManager`]
+//! - [`multicall`] - Batched reads through Multicall3
+//! - [`failover`] - Failover across RPC endpoints via [`FailoverProvider`]
+//! - [`pool`] - Per-wallet RPC endpoint assignment via [`ProviderPool`]
+//! - [`fault`] - Fault injection into the mock providers via
+//!   [`FaultPlan`](fault::FaultPlan)
 //! - [`error`] - Error types with detailed context
 //!
 //! # Feature Flags
@@ -88,6 +96,7 @@
 //! |----------|--------------|-------------------|
 //! | `StandardEvmProvider` | Any EVM chain | No |
 //! | `MegaEthProvider` | MegaETH | Realtime API, cursor pagination |
+//! | `FailoverProvider` | Wrapped providers' chain | Those of the wrapped providers |
 //!
 //! # Architecture
 //!
@@ -121,14 +130,20 @@
 //! ```
 
 #![doc(html_root_url = "https://docs.ghostnet.io/evm-provider")]
+#![cfg_attr(test, allow(clippy::unwrap_used, clippy::expect_used, clippy::stable_sort_primitive))]
 
 // ═══════════════════════════════════════════════════════════════════════════════
 // MODULES
 // ═══════════════════════════════════════════════════════════════════════════════
 
 pub mod error;
+pub mod failover;
+pub mod fault;
 pub mod mock;
+pub mod multicall;
 pub mod nonce;
+pub mod pool;
+pub mod signer;
 pub mod standard;
 pub mod traits;
 pub mod types;
@@ -137,13 +152,20 @@ pub mod types;
 #[cfg(feature = "megaeth")]
 pub mod megaeth;
 
+// Scriptable mock for tests (requires test-utils feature)
+#[cfg(feature = "test-utils")]
+pub mod mock_chain;
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // RE-EXPORTS
 // ═══════════════════════════════════════════════════════════════════════════════
 
 // Primary types - what most users need
 pub use error::{ProviderError, Result};
-pub use nonce::LocalNonceManager;
+pub use failover::{EndpointHealth, FailoverProvider};
+pub use nonce::{LocalNonceManager, PendingTracker, PendingTx};
+pub use pool::{AssignmentStrategy, EndpointStats, PoolEndpoint, ProviderPool};
+pub use signer::{LocalSigner, RemoteHttpSigner, TransactionSigner};
 pub use standard::StandardEvmProvider;
 pub use traits::{ChainProvider, ExtendedChainProvider, NonceManager};
 pub use types::{LogFilter, LogsPage, TransactionReceipt, TransactionRequest};
@@ -165,7 +187,9 @@ pub use megaeth::MegaEthProvider;
 /// ```
 pub mod prelude {
     pub use crate::error::{ProviderError, Result};
+    pub use crate::failover::FailoverProvider;
     pub use crate::nonce::LocalNonceManager;
+    pub use crate::signer::{LocalSigner, TransactionSigner};
     pub use crate::standard::StandardEvmProvider;
     pub use crate::traits::{ChainProvider, ExtendedChainProvider, NonceManager};
     pub use crate::types::{LogFilter, LogsPage, TransactionReceipt, TransactionRequest};
@@ -189,7 +189,8 @@ impl MegaEthProvider {
 
     /// Set the fixed gas limit used for transactions.
     ///
-    /// MegaETH gas estimation is unreliable, so we use a fixed limit.
+    /// MegaETH gas estimation is unreliable, so we use a fixed limit. The
+    /// estimate is still run, so transactions that would revert are caught.
     /// Default is 10,000,000 (10M).
     #[must_use]
     pub const fn with_gas_limit(mut self, gas_limit: u64) -> Self {
@@ -233,6 +234,10 @@ impl ChainProvider for MegaEthProvider {
         self.standard.chain_id()
     }
 
+    async fn get_chain_id(&self) -> Result<u64> {
+        self.standard.get_chain_id().await
+    }
+
     async fn get_balance(&self, address: Address) -> Result<U256> {
         self.standard.get_balance(address).await
     }
@@ -262,12 +267,20 @@ impl ChainProvider for MegaEthProvider {
     /// MegaETH's gas estimation is unreliable and often returns "intrinsic gas too low"
     /// errors. We return a fixed gas limit instead.
     #[instrument(skip(self, _tx))]
-    async fn estimate_gas(&self, _tx: &TransactionRequest) -> Result<u64> {
-        debug!(
-            gas = self.fixed_gas_limit,
-            "Using fixed gas limit for MegaETH (estimation unreliable)"
-        );
-        Ok(self.fixed_gas_limit)
+    async fn estimate_gas(&self, tx: &TransactionRequest) -> Result<u64> {
+        // The estimate itself is unreliable, but a revert is not: running it
+        // keeps transactions bound to fail from being sent
+        match self.standard.estimate_gas(tx).await {
+            Err(e) if e.revert_reason().is_some() => Err(e),
+            estimated => {
+                debug!(
+                    gas = self.fixed_gas_limit,
+                    estimated = estimated.ok(),
+                    "Using fixed gas limit for MegaETH (estimation unreliable)"
+                );
+                Ok(self.fixed_gas_limit)
+            }
+        }
     }
 
     async fn gas_price(&self) -> Result<u128> {
@@ -281,6 +294,10 @@ impl ChainProvider for MegaEthProvider {
     async fn call(&self, tx: &TransactionRequest) -> Result<Bytes> {
         self.standard.call(tx).await
     }
+
+    fn supports_multicall(&self) -> bool {
+        self.standard.supports_multicall()
+    }
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
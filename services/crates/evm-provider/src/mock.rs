@@ -1,7 +1,8 @@
 //! Mock provider for testing.
 //!
 //! This module provides a [`MockProvider`] that implements [`ChainProvider`]
-//! for use in tests without needing a real blockchain connection.
+//! and [`ExtendedChainProvider`] for use in tests without needing a real
+//! blockchain connection.
 //!
 //! # Panics
 //!
@@ -13,18 +14,120 @@
 #![allow(clippy::expect_used)]
 #![allow(clippy::missing_panics_doc)]
 
-use std::collections::HashMap;
-use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::RwLock;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
 use std::time::Duration;
 
+use alloy::consensus::{Transaction as _, TxEnvelope};
+use alloy::eips::Decodable2718;
 use alloy::primitives::{Address, Bytes, TxHash, U256};
 use async_trait::async_trait;
 
-use crate::error::Result;
-use crate::traits::ChainProvider;
+use crate::error::{ProviderError, Result};
+use crate::fault::{FaultPlan, Method};
+use crate::traits::{ChainProvider, ExtendedChainProvider};
 use crate::types::{TransactionReceipt, TransactionRequest};
 
+/// How long a realtime submission of a dropped transaction waits before it
+/// times out, as MegaETH's realtime API does.
+const REALTIME_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Gas any transaction uses before it runs, as nodes check on submission.
+const INTRINSIC_GAS: u64 = 21_000;
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TRANSACTION OUTCOMES
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Distribution of simulated transaction outcomes.
+///
+/// Each sent transaction draws its outcome from a deterministic sequence
+/// seeded by `seed`, so the same sequence of sends always yields the same
+/// receipts. The default mines every transaction instantly using 50k gas.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TxOutcomes {
+    /// Fraction of mined transactions that revert (0.0 - 1.0).
+    pub revert_rate: f64,
+
+    /// Fraction of transactions that are never mined (0.0 - 1.0).
+    pub drop_rate: f64,
+
+    /// Inclusive range of gas used by mined transactions.
+    pub gas_used: (u64, u64),
+
+    /// Inclusive range of time until the receipt is available.
+    ///
+    /// Waiting for a receipt with a shorter timeout fails as if the
+    /// transaction was dropped.
+    pub latency: (Duration, Duration),
+
+    /// Seed of the outcome sequence.
+    pub seed: u64,
+}
+
+impl Default for TxOutcomes {
+    fn default() -> Self {
+        Self {
+            revert_rate: 0.0,
+            drop_rate: 0.0,
+            gas_used: (50_000, 50_000),
+            latency: (Duration::ZERO, Duration::ZERO),
+            seed: 0,
+        }
+    }
+}
+
+impl TxOutcomes {
+    /// Draw the outcome of the `index`th transaction.
+    fn draw(&self, index: u64) -> SentTx {
+        let sample = |stream: u64| splitmix64(self.seed ^ splitmix64(index * 4 + stream));
+        let fraction = |stream: u64| {
+            f64::from(u32::try_from(sample(stream) >> 32).unwrap_or(u32::MAX))
+                / f64::from(u32::MAX)
+        };
+        let in_range = |stream: u64, (min, max): (u64, u64)| {
+            let span = max.saturating_sub(min).saturating_add(1);
+            min + sample(stream) % span
+        };
+        let millis = |d: Duration| u64::try_from(d.as_millis()).unwrap_or(u64::MAX);
+
+        SentTx {
+            dropped: fraction(0) < self.drop_rate,
+            reverted: fraction(1) < self.revert_rate,
+            gas_used: in_range(2, self.gas_used),
+            latency: Duration::from_millis(in_range(
+                3,
+                (millis(self.latency.0), millis(self.latency.1)),
+            )),
+        }
+    }
+}
+
+/// Outcome drawn for a sent transaction.
+#[derive(Debug, Clone, Copy)]
+struct SentTx {
+    /// Never mined.
+    dropped: bool,
+
+    /// Mined but reverted.
+    reverted: bool,
+
+    /// Gas used if mined.
+    gas_used: u64,
+
+    /// Time until the receipt is available.
+    latency: Duration,
+}
+
+/// `SplitMix64` step, a small well-mixed hash for outcome draws.
+pub(crate) const fn splitmix64(x: u64) -> u64 {
+    let mut z = x.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // MOCK PROVIDER
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -64,6 +167,21 @@ pub struct MockProvider {
     /// Token balances by (token, account).
     token_balances: RwLock<HashMap<(Address, Address), U256>>,
 
+    /// Token balance reads that fail, by (token, account).
+    failing_token_balances: RwLock<HashSet<(Address, Address)>>,
+
+    /// Whether batched token balance reads are supported.
+    multicall: AtomicBool,
+
+    /// Whether realtime transaction submission is supported.
+    realtime: AtomicBool,
+
+    /// Sizes of the batched token balance reads served so far.
+    token_balance_batches: RwLock<Vec<usize>>,
+
+    /// Number of single token balance reads served so far.
+    token_balance_reads: AtomicU64,
+
     /// Gas price in wei.
     gas_price: AtomicU64,
 
@@ -72,6 +190,18 @@ pub struct MockProvider {
 
     /// Call responses by (to, data selector).
     call_responses: RwLock<HashMap<(Address, [u8; 4]), Bytes>>,
+
+    /// Distribution of outcomes for sent transactions.
+    tx_outcomes: RwLock<TxOutcomes>,
+
+    /// Outcomes of sent transactions by hash.
+    sent: RwLock<HashMap<TxHash, SentTx>>,
+
+    /// Whether the endpoint is down, failing every request.
+    offline: AtomicBool,
+
+    /// Faults injected into requests.
+    fault_plan: RwLock<Option<Arc<FaultPlan>>>,
 }
 
 impl Default for MockProvider {
@@ -95,9 +225,18 @@ impl MockProvider {
             balances: RwLock::new(HashMap::new()),
             nonces: RwLock::new(HashMap::new()),
             token_balances: RwLock::new(HashMap::new()),
+            failing_token_balances: RwLock::new(HashSet::new()),
+            multicall: AtomicBool::new(false),
+            realtime: AtomicBool::new(false),
+            token_balance_batches: RwLock::new(Vec::new()),
+            token_balance_reads: AtomicU64::new(0),
             gas_price: AtomicU64::new(1_000_000_000), // 1 gwei
             tx_counter: AtomicU64::new(1),
             call_responses: RwLock::new(HashMap::new()),
+            tx_outcomes: RwLock::new(TxOutcomes::default()),
+            sent: RwLock::new(HashMap::new()),
+            offline: AtomicBool::new(false),
+            fault_plan: RwLock::new(None),
         }
     }
 
@@ -125,6 +264,59 @@ impl MockProvider {
             .insert((token, account), balance);
     }
 
+    /// Make reads of a token balance fail.
+    pub fn fail_token_balance(&self, token: Address, account: Address) {
+        self.failing_token_balances
+            .write()
+            .expect("lock poisoned")
+            .insert((token, account));
+    }
+
+    /// Enable or disable batched token balance reads.
+    pub fn set_multicall_support(&self, enabled: bool) {
+        self.multicall.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Enable or disable realtime transaction submission.
+    pub fn set_realtime_support(&self, enabled: bool) {
+        self.realtime.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Sizes of the batched token balance reads served so far.
+    #[must_use]
+    pub fn token_balance_batches(&self) -> Vec<usize> {
+        self.token_balance_batches
+            .read()
+            .expect("lock poisoned")
+            .clone()
+    }
+
+    /// Number of single token balance reads served so far.
+    #[must_use]
+    pub fn token_balance_reads(&self) -> u64 {
+        self.token_balance_reads.load(Ordering::Relaxed)
+    }
+
+    /// Look up a token balance, honouring [`fail_token_balance`](Self::fail_token_balance).
+    fn lookup_token_balance(&self, token: Address, account: Address) -> Option<U256> {
+        if self
+            .failing_token_balances
+            .read()
+            .expect("lock poisoned")
+            .contains(&(token, account))
+        {
+            return None;
+        }
+        Some(
+            self.token_balances
+                .read()
+                .expect("lock poisoned")
+                .get(&(token, account))
+                .copied()
+                .unwrap_or(U256::ZERO),
+        )
+    }
+
     /// Set the gas price.
     pub fn set_gas_price(&self, price: u64) {
         self.gas_price.store(price, Ordering::Relaxed);
@@ -141,6 +333,56 @@ impl MockProvider {
             .insert((to, selector), response);
     }
 
+    /// Set the distribution of outcomes for transactions sent from now on.
+    pub fn set_tx_outcomes(&self, outcomes: TxOutcomes) {
+        *self.tx_outcomes.write().expect("lock poisoned") = outcomes;
+    }
+
+    /// Time until the receipt of a sent transaction is available.
+    ///
+    /// Returns `None` for transactions this provider did not send.
+    #[must_use]
+    pub fn receipt_latency(&self, tx_hash: TxHash) -> Option<Duration> {
+        self.sent
+            .read()
+            .expect("lock poisoned")
+            .get(&tx_hash)
+            .map(|tx| tx.latency)
+    }
+
+    /// Take the endpoint down or bring it back up.
+    ///
+    /// While offline, every request fails with a connection error, as an
+    /// unreachable RPC endpoint would.
+    pub fn set_offline(&self, offline: bool) {
+        self.offline.store(offline, Ordering::Relaxed);
+    }
+
+    /// Inject the faults of `plan` into every request.
+    pub fn set_fault_plan(&self, plan: Arc<FaultPlan>) {
+        *self.fault_plan.write().expect("lock poisoned") = Some(plan);
+    }
+
+    /// Fail a request to `method` if the endpoint is offline or the fault
+    /// plan says so, after the plan's latency; `tx` is the raw transaction
+    /// of a send.
+    ///
+    /// Returns whether the sent transaction reverts.
+    async fn serve(&self, method: Method, tx: Option<&Bytes>) -> Result<bool> {
+        if self.offline.load(Ordering::Relaxed) {
+            return Err(ProviderError::Connection("mock endpoint offline".into()));
+        }
+        let plan = self.fault_plan.read().expect("lock poisoned").clone();
+        let Some(plan) = plan else {
+            return Ok(false);
+        };
+        let injection = plan.inject(method, tx);
+        if !injection.latency.is_zero() {
+            tokio::time::sleep(injection.latency).await;
+        }
+        injection.error.map_or(Ok(injection.revert), Err)
+    }
+
     /// Generate a mock transaction hash.
     fn next_tx_hash(&self) -> TxHash {
         let counter = self.tx_counter.fetch_add(1, Ordering::Relaxed);
@@ -148,6 +390,52 @@ impl MockProvider {
         bytes[24..32].copy_from_slice(&counter.to_be_bytes());
         TxHash::from(bytes)
     }
+
+    /// Accept a sent transaction, deciding its fate up front.
+    fn accept(&self, revert: bool) -> (TxHash, SentTx) {
+        let index = self.tx_counter.load(Ordering::Relaxed);
+        let tx_hash = self.next_tx_hash();
+        let mut outcome = self.tx_outcomes.read().expect("lock poisoned").draw(index);
+        outcome.reverted |= revert;
+        self.sent
+            .write()
+            .expect("lock poisoned")
+            .insert(tx_hash, outcome);
+        (tx_hash, outcome)
+    }
+}
+
+/// Refuse a signed transaction `raw` that does not even pay for its
+/// intrinsic gas, as a node does before looking at any state.
+///
+/// Bytes that are not a transaction, which tests send as markers, pass.
+fn refuse(raw: &Bytes) -> Result<()> {
+    match TxEnvelope::decode_2718(&mut raw.as_ref()) {
+        Ok(tx) if tx.gas_limit() < INTRINSIC_GAS => Err(ProviderError::rpc(
+            -32000,
+            format!(
+                "intrinsic gas too low: have {}, want {INTRINSIC_GAS}",
+                tx.gas_limit()
+            ),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Receipt of a transaction, successful unless drawn otherwise.
+fn receipt(tx_hash: TxHash, sent: Option<SentTx>) -> TransactionReceipt {
+    TransactionReceipt {
+        tx_hash,
+        block_hash: alloy::primitives::B256::ZERO,
+        block_number: 12345,
+        tx_index: 0,
+        from: Address::ZERO,
+        to: None,
+        contract_address: None,
+        gas_used: sent.map_or(50000, |tx| tx.gas_used),
+        success: !sent.is_some_and(|tx| tx.reverted),
+        logs: vec![],
+    }
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -164,7 +452,13 @@ impl ChainProvider for MockProvider {
         self
     }
 
+    async fn get_chain_id(&self) -> Result<u64> {
+        self.serve(Method::ChainId, None).await?;
+        Ok(self.chain_id)
+    }
+
     async fn get_balance(&self, address: Address) -> Result<U256> {
+        self.serve(Method::Balance, None).await?;
         Ok(self
             .balances
             .read()
@@ -175,16 +469,37 @@ impl ChainProvider for MockProvider {
     }
 
     async fn get_token_balance(&self, token: Address, account: Address) -> Result<U256> {
-        Ok(self
-            .token_balances
-            .read()
+        self.serve(Method::TokenBalance, None).await?;
+        self.token_balance_reads.fetch_add(1, Ordering::Relaxed);
+        self.lookup_token_balance(token, account).ok_or_else(|| {
+            ProviderError::InvalidResponse(format!("balanceOf({account}) on {token} reverted"))
+        })
+    }
+
+    fn supports_multicall(&self) -> bool {
+        self.multicall.load(Ordering::Relaxed)
+    }
+
+    async fn get_token_balances(
+        &self,
+        queries: &[(Address, Address)],
+    ) -> Result<Vec<Option<U256>>> {
+        self.serve(Method::TokenBalance, None).await?;
+        if !self.supports_multicall() {
+            return Err(ProviderError::unsupported("multicall"));
+        }
+        self.token_balance_batches
+            .write()
             .expect("lock poisoned")
-            .get(&(token, account))
-            .copied()
-            .unwrap_or(U256::ZERO))
+            .push(queries.len());
+        Ok(queries
+            .iter()
+            .map(|&(token, account)| self.lookup_token_balance(token, account))
+            .collect())
     }
 
     async fn get_nonce(&self, address: Address) -> Result<u64> {
+        self.serve(Method::Nonce, None).await?;
         Ok(self
             .nonces
             .read()
@@ -199,45 +514,44 @@ impl ChainProvider for MockProvider {
         self.get_nonce(address).await
     }
 
-    async fn send_raw_transaction(&self, _tx: Bytes) -> Result<TxHash> {
-        // Return a mock transaction hash
-        Ok(self.next_tx_hash())
+    async fn send_raw_transaction(&self, tx: Bytes) -> Result<TxHash> {
+        let revert = self.serve(Method::SendRawTransaction, Some(&tx)).await?;
+        refuse(&tx)?;
+        Ok(self.accept(revert).0)
     }
 
     async fn get_block_number(&self) -> Result<u64> {
+        self.serve(Method::BlockNumber, None).await?;
         Ok(12345) // Fixed mock block number
     }
 
     async fn wait_for_receipt(
         &self,
         tx_hash: TxHash,
-        _timeout: Duration,
+        timeout: Duration,
     ) -> Result<TransactionReceipt> {
-        // Return a mock successful receipt
-        Ok(TransactionReceipt {
-            tx_hash,
-            block_hash: alloy::primitives::B256::ZERO,
-            block_number: 12345,
-            tx_index: 0,
-            from: Address::ZERO,
-            to: None,
-            contract_address: None,
-            gas_used: 50000,
-            success: true,
-            logs: vec![],
-        })
+        self.serve(Method::WaitForReceipt, None).await?;
+        let sent = self.sent.read().expect("lock poisoned").get(&tx_hash).copied();
+        if sent.is_some_and(|tx| tx.dropped || tx.latency > timeout) {
+            return Err(ProviderError::Timeout(timeout));
+        }
+
+        Ok(receipt(tx_hash, sent))
     }
 
     async fn estimate_gas(&self, _tx: &TransactionRequest) -> Result<u64> {
+        self.serve(Method::EstimateGas, None).await?;
         // Return a reasonable default
         Ok(100_000)
     }
 
     async fn gas_price(&self) -> Result<u128> {
+        self.serve(Method::GasPrice, None).await?;
         Ok(u128::from(self.gas_price.load(Ordering::Relaxed)))
     }
 
     async fn call(&self, tx: &TransactionRequest) -> Result<Bytes> {
+        self.serve(Method::Call, None).await?;
         // Check if we have a registered response
         if let (Some(to), Some(data)) = (&tx.to, &tx.data)
             && data.len() >= 4
@@ -260,6 +574,28 @@ impl ChainProvider for MockProvider {
     }
 }
 
+/// Realtime submission returns the receipt right away, whatever its drawn
+/// latency; a transaction drawn to be dropped times out.
+#[async_trait]
+impl ExtendedChainProvider for MockProvider {
+    fn supports_realtime(&self) -> bool {
+        self.realtime.load(Ordering::Relaxed)
+    }
+
+    async fn send_realtime(&self, tx: Bytes) -> Result<TransactionReceipt> {
+        if !self.supports_realtime() {
+            return Err(ProviderError::unsupported("realtime transactions"));
+        }
+        let revert = self.serve(Method::SendRealtime, Some(&tx)).await?;
+        refuse(&tx)?;
+        let (tx_hash, sent) = self.accept(revert);
+        if sent.dropped {
+            return Err(ProviderError::Timeout(REALTIME_TIMEOUT));
+        }
+        Ok(receipt(tx_hash, Some(sent)))
+    }
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // TESTS
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -267,6 +603,7 @@ impl ChainProvider for MockProvider {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::fault::Fault;
 
     #[tokio::test]
     async fn default_balance_is_zero() {
@@ -300,6 +637,100 @@ mod tests {
         assert_eq!(balance, amount);
     }
 
+    #[tokio::test]
+    async fn fault_plans_fail_and_revert() {
+        let provider = MockProvider::new();
+        let plan = Arc::new(
+            FaultPlan::new(0)
+                .fail_every(Method::Balance, 2, Fault::Connection)
+                .revert_matching(|raw| raw.as_ref() == b"extract"),
+        );
+        provider.set_fault_plan(Arc::clone(&plan));
+
+        assert!(provider.get_balance(Address::ZERO).await.is_ok());
+        assert!(matches!(
+            provider.get_balance(Address::ZERO).await,
+            Err(ProviderError::Connection(_))
+        ));
+        let reverted = provider
+            .send_raw_transaction(Bytes::from_static(b"extract"))
+            .await
+            .unwrap();
+        let mined = provider
+            .send_raw_transaction(Bytes::from_static(b"jack_in"))
+            .await
+            .unwrap();
+        for (tx_hash, success) in [(reverted, false), (mined, true)] {
+            let receipt = provider.wait_for_receipt(tx_hash, Duration::ZERO).await;
+            assert_eq!(receipt.unwrap().success, success);
+        }
+
+        assert_eq!(plan.calls(Method::Balance), 2);
+        assert_eq!(plan.calls(Method::WaitForReceipt), 2);
+        assert_eq!(plan.total_faults(), 2);
+    }
+
+    #[tokio::test]
+    async fn realtime_sends_return_the_receipt() {
+        let provider = MockProvider::new();
+        let tx = Bytes::from_static(b"extract");
+        assert!(matches!(
+            provider.send_realtime(tx.clone()).await,
+            Err(ProviderError::Unsupported(_))
+        ));
+
+        provider.set_realtime_support(true);
+        let plan = Arc::new(
+            FaultPlan::new(0)
+                .fail_next(Method::SendRealtime, 1, Fault::Connection)
+                .revert_matching(|raw| raw.as_ref() == b"extract"),
+        );
+        provider.set_fault_plan(Arc::clone(&plan));
+        assert!(provider.send_realtime(tx.clone()).await.unwrap_err().is_retryable());
+        let receipt = provider.send_realtime(tx).await.unwrap();
+        assert!(!receipt.success);
+        assert_eq!(plan.calls(Method::SendRealtime), 2);
+        assert_eq!(plan.calls(Method::WaitForReceipt), 0);
+
+        provider.set_tx_outcomes(TxOutcomes {
+            drop_rate: 1.0,
+            ..TxOutcomes::default()
+        });
+        assert!(matches!(
+            provider.send_realtime(Bytes::new()).await,
+            Err(ProviderError::Timeout(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn transactions_short_of_intrinsic_gas_are_refused() {
+        use alloy::signers::local::PrivateKeySigner;
+
+        use crate::signer::{LocalSigner, TransactionSigner};
+
+        let provider = MockProvider::new();
+        provider.set_realtime_support(true);
+        let signer = LocalSigner::new(PrivateKeySigner::random());
+        let request = |gas_limit| {
+            TransactionRequest::new()
+                .to(Address::ZERO)
+                .nonce(0)
+                .gas_limit(gas_limit)
+                .gas_price(1)
+        };
+
+        let short = signer.sign_transaction(&request(1), 31337).await.unwrap();
+        let refused = provider
+            .send_raw_transaction(short.clone())
+            .await
+            .unwrap_err();
+        assert!(refused.is_refusal(), "{refused}");
+        assert!(provider.send_realtime(short).await.unwrap_err().is_refusal());
+
+        let paid = signer.sign_transaction(&request(21_000), 31337).await.unwrap();
+        assert!(provider.send_raw_transaction(paid).await.is_ok());
+    }
+
     #[tokio::test]
     async fn nonces() {
         let provider = MockProvider::new();
@@ -338,6 +769,42 @@ mod tests {
         assert_eq!(receipt.tx_hash, tx_hash);
     }
 
+    #[tokio::test]
+    async fn tx_outcomes_are_seeded() {
+        let outcomes = TxOutcomes {
+            revert_rate: 0.3,
+            drop_rate: 0.2,
+            gas_used: (40_000, 60_000),
+            latency: (Duration::from_millis(100), Duration::from_secs(2)),
+            seed: 7,
+        };
+        let run = || async {
+            let provider = MockProvider::new();
+            provider.set_tx_outcomes(outcomes);
+            let mut results = Vec::new();
+            for _ in 0..50 {
+                let hash = provider.send_raw_transaction(Bytes::new()).await.unwrap();
+                let receipt = provider.wait_for_receipt(hash, Duration::from_secs(1)).await;
+                results.push(receipt.map(|r| (r.success, r.gas_used)).ok());
+            }
+            results
+        };
+
+        let first = run().await;
+        assert_eq!(first, run().await);
+
+        // Some of each outcome, gas within range
+        assert!(first.contains(&None));
+        assert!(first.iter().flatten().any(|(success, _)| !success));
+        assert!(first.iter().flatten().any(|(success, _)| *success));
+        assert!(
+            first
+                .iter()
+                .flatten()
+                .all(|(_, gas)| (40_000..=60_000).contains(gas))
+        );
+    }
+
     #[tokio::test]
     async fn chain_id() {
         let provider = MockProvider::new();
@@ -0,0 +1,829 @@
+//! Scriptable mock provider for tests (requires the `test-utils` feature).
+//!
+//! [`MockProvider`](crate::mock::MockProvider) simulates a chain with fixed
+//! behaviour. [`MockChainProvider`] is for tests that need to say exactly
+//! what the chain answers and check what was asked of it:
+//!
+//! - **State**: balances, nonces and token balances per address, call results
+//!   keyed by contract and selector, receipts by transaction hash
+//! - **Scripts**: a queue of responses per [`Method`], served before the state
+//! - **Injection**: errors, reverted transactions and latency per method, or
+//!   a whole [`FaultPlan`]
+//! - **Recording**: every request, in order, as a [`RecordedCall`]
+//!
+//! # Example
+//!
+//! ```
+//! use alloy::primitives::{Address, Bytes};
+//! use evm_provider::mock_chain::{Method, MockChainProvider};
+//! use evm_provider::{ChainProvider, ProviderError};
+//!
+//! # #[tokio::main]
+//! # async fn main() {
+//! let chain = MockChainProvider::new();
+//! chain.fail_next(Method::SendRawTransaction, ProviderError::Connection("down".into()));
+//!
+//! let raw = Bytes::from_static(b"signed tx");
+//! assert!(chain.send_raw_transaction(raw.clone()).await.is_err());
+//!
+//! // The retry goes through and is mined, unless told to revert
+//! chain.revert_next_transaction();
+//! let tx_hash = chain.send_raw_transaction(raw.clone()).await.unwrap();
+//! assert_eq!(tx_hash, MockChainProvider::tx_hash_of(&raw));
+//! let receipt = chain.wait_for_receipt(tx_hash, Default::default()).await.unwrap();
+//! assert!(!receipt.success);
+//!
+//! assert_eq!(chain.sent_transactions(), [raw.clone(), raw]);
+//! # }
+//! ```
+//!
+//! # Panics
+//!
+//! Methods panic if an internal lock is poisoned, or if a scripted
+//! [`Response`] does not fit the method it was queued for. Both indicate a
+//! bug in the test.
+
+#![allow(clippy::expect_used)]
+#![allow(clippy::missing_panics_doc)]
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use alloy::primitives::{Address, B256, Bytes, TxHash, U256, keccak256};
+use alloy::rpc::types::Log;
+use async_trait::async_trait;
+
+use crate::error::{ProviderError, Result};
+use crate::fault::FaultPlan;
+use crate::traits::ChainProvider;
+use crate::types::{TransactionReceipt, TransactionRequest};
+
+/// Block number served unless set otherwise.
+const DEFAULT_BLOCK_NUMBER: u64 = 1_000;
+
+/// Gas used by default receipts.
+const DEFAULT_GAS_USED: u64 = 50_000;
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// METHODS AND RESPONSES
+// ═══════════════════════════════════════════════════════════════════════════════
+
+pub use crate::fault::Method;
+
+/// A scripted response, see [`MockChainProvider::push_response`].
+#[derive(Debug, Clone)]
+pub enum Response {
+    /// A chain ID, for [`Method::ChainId`].
+    ChainId(u64),
+    /// An amount, for [`Method::Balance`] and [`Method::TokenBalance`].
+    Amount(U256),
+    /// A nonce, for [`Method::Nonce`].
+    Nonce(u64),
+    /// A transaction hash, for [`Method::SendRawTransaction`].
+    TxHash(TxHash),
+    /// A receipt, for [`Method::WaitForReceipt`].
+    Receipt(Box<TransactionReceipt>),
+    /// A gas amount, for [`Method::EstimateGas`].
+    Gas(u64),
+    /// A gas price, for [`Method::GasPrice`].
+    GasPrice(u128),
+    /// A block number, for [`Method::BlockNumber`].
+    BlockNumber(u64),
+    /// Return data, for [`Method::Call`].
+    Data(Bytes),
+}
+
+/// A request made to the mock, in the order it was made.
+#[derive(Debug, Clone)]
+pub enum RecordedCall {
+    /// `get_chain_id`.
+    ChainId,
+    /// `get_balance(address)`.
+    Balance(Address),
+    /// `get_nonce(address)` or `get_pending_nonce(address)`.
+    Nonce(Address),
+    /// `get_token_balance(token, account)`.
+    TokenBalance {
+        /// Token contract.
+        token: Address,
+        /// Account holding the token.
+        account: Address,
+    },
+    /// `send_raw_transaction(raw)`.
+    SendRawTransaction(Bytes),
+    /// `wait_for_receipt(tx_hash)`.
+    WaitForReceipt(TxHash),
+    /// `estimate_gas(tx)`.
+    EstimateGas(TransactionRequest),
+    /// `gas_price`.
+    GasPrice,
+    /// `get_block_number`.
+    BlockNumber,
+    /// `call(tx)`.
+    Call(TransactionRequest),
+}
+
+impl RecordedCall {
+    /// The method the request was made to.
+    #[must_use]
+    pub const fn method(&self) -> Method {
+        match self {
+            Self::ChainId => Method::ChainId,
+            Self::Balance(_) => Method::Balance,
+            Self::Nonce(_) => Method::Nonce,
+            Self::TokenBalance { .. } => Method::TokenBalance,
+            Self::SendRawTransaction(_) => Method::SendRawTransaction,
+            Self::WaitForReceipt(_) => Method::WaitForReceipt,
+            Self::EstimateGas(_) => Method::EstimateGas,
+            Self::GasPrice => Method::GasPrice,
+            Self::BlockNumber => Method::BlockNumber,
+            Self::Call(_) => Method::Call,
+        }
+    }
+}
+
+/// Persistent outcome of calls to a contract function.
+#[derive(Debug, Clone)]
+enum CallResult {
+    /// The call returns this data.
+    Data(Bytes),
+    /// The call reverts with this reason.
+    Revert(String),
+}
+
+/// Everything the mock knows, behind one lock.
+#[derive(Debug, Default)]
+struct State {
+    balances: HashMap<Address, U256>,
+    nonces: HashMap<Address, u64>,
+    token_balances: HashMap<(Address, Address), U256>,
+    call_results: HashMap<(Address, [u8; 4]), CallResult>,
+    receipts: HashMap<TxHash, TransactionReceipt>,
+    /// Transactions sent, with whether they revert, by hash.
+    sent: HashMap<TxHash, bool>,
+    /// Transactions sent that are never mined.
+    dropped: HashSet<TxHash>,
+    scripts: HashMap<Method, VecDeque<Result<Response>>>,
+    latency: HashMap<Method, Duration>,
+    fault_plan: Option<Arc<FaultPlan>>,
+    calls: Vec<RecordedCall>,
+    sent_raw: Vec<Bytes>,
+    revert_next: bool,
+    drop_next: bool,
+    gas_price: u128,
+    block_number: u64,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// MOCK CHAIN PROVIDER
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Scriptable, recording mock of a [`ChainProvider`].
+///
+/// Each request first pops the [scripted](Self::push_response) queue of its
+/// method. With nothing queued, it is answered from the state set up on the
+/// mock, with defaults for everything unset: zero balances and nonces, empty
+/// call results, 1 gwei gas and mined, successful receipts.
+///
+/// Sent transactions get the hash [`tx_hash_of`](Self::tx_hash_of) their raw
+/// bytes, so tests can set up their receipts before sending.
+#[derive(Debug)]
+pub struct MockChainProvider {
+    /// Chain ID.
+    chain_id: u64,
+
+    /// Mutable state.
+    state: Mutex<State>,
+
+    /// Whether token balances are read with `balanceOf` calls instead of the
+    /// token balance map.
+    token_calls: AtomicBool,
+}
+
+impl Default for MockChainProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MockChainProvider {
+    /// Create a mock with the default anvil chain ID.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_chain_id(31337)
+    }
+
+    /// Create a mock with a specific chain ID.
+    #[must_use]
+    pub fn with_chain_id(chain_id: u64) -> Self {
+        Self {
+            chain_id,
+            state: Mutex::new(State {
+                gas_price: 1_000_000_000,
+                block_number: DEFAULT_BLOCK_NUMBER,
+                ..State::default()
+            }),
+            token_calls: AtomicBool::new(false),
+        }
+    }
+
+    /// Hash a sent transaction gets.
+    #[must_use]
+    pub fn tx_hash_of(raw: &Bytes) -> TxHash {
+        keccak256(raw)
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().expect("lock poisoned")
+    }
+
+    // ───────────────────────────────────────────────────────────────────────────
+    // State
+    // ───────────────────────────────────────────────────────────────────────────
+
+    /// Set the native balance of an address.
+    pub fn set_balance(&self, address: Address, balance: U256) {
+        self.state().balances.insert(address, balance);
+    }
+
+    /// Set the nonce of an address.
+    pub fn set_nonce(&self, address: Address, nonce: u64) {
+        self.state().nonces.insert(address, nonce);
+    }
+
+    /// Set a token balance.
+    pub fn set_token_balance(&self, token: Address, account: Address, balance: U256) {
+        self.state()
+            .token_balances
+            .insert((token, account), balance);
+    }
+
+    /// Read token balances through `balanceOf` [calls](Self::set_call_result)
+    /// instead of the token balances set on the mock.
+    pub fn read_token_balances_by_call(&self, enabled: bool) {
+        self.token_calls.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Make calls of `selector` on `to` return `data`.
+    pub fn set_call_result(&self, to: Address, selector: [u8; 4], data: impl Into<Bytes>) {
+        self.state()
+            .call_results
+            .insert((to, selector), CallResult::Data(data.into()));
+    }
+
+    /// Make calls of `selector` on `to` revert with `reason`.
+    pub fn revert_call(&self, to: Address, selector: [u8; 4], reason: impl Into<String>) {
+        self.state()
+            .call_results
+            .insert((to, selector), CallResult::Revert(reason.into()));
+    }
+
+    /// Set the receipt of a transaction, sent or not.
+    pub fn set_receipt(&self, receipt: TransactionReceipt) {
+        self.state().receipts.insert(receipt.tx_hash, receipt);
+    }
+
+    /// Emit `logs` in the receipt of the transaction sent as `raw`.
+    pub fn set_receipt_logs(&self, raw: &Bytes, logs: Vec<Log>) {
+        let tx_hash = Self::tx_hash_of(raw);
+        let mut receipt = self.default_receipt(tx_hash, false);
+        receipt.logs = logs;
+        self.set_receipt(receipt);
+    }
+
+    /// Set the gas price.
+    pub fn set_gas_price(&self, price: u128) {
+        self.state().gas_price = price;
+    }
+
+    /// Set the block number.
+    pub fn set_block_number(&self, block_number: u64) {
+        self.state().block_number = block_number;
+    }
+
+    // ───────────────────────────────────────────────────────────────────────────
+    // Scripts and injection
+    // ───────────────────────────────────────────────────────────────────────────
+
+    /// Queue a response for the next request to `method` that has none
+    /// queued before it.
+    pub fn push_response(&self, method: Method, response: Result<Response>) {
+        self.state()
+            .scripts
+            .entry(method)
+            .or_default()
+            .push_back(response);
+    }
+
+    /// Fail the next request to `method` with `error`.
+    pub fn fail_next(&self, method: Method, error: ProviderError) {
+        self.push_response(method, Err(error));
+    }
+
+    /// Make the next transaction sent be mined, but revert.
+    pub fn revert_next_transaction(&self) {
+        self.state().revert_next = true;
+    }
+
+    /// Make the next transaction sent never be mined.
+    pub fn drop_next_transaction(&self) {
+        self.state().drop_next = true;
+    }
+
+    /// Delay every response of `method` by `latency`.
+    pub fn set_latency(&self, method: Method, latency: Duration) {
+        self.state().latency.insert(method, latency);
+    }
+
+    /// Inject the faults of `plan` into requests nothing is scripted for.
+    pub fn set_fault_plan(&self, plan: Arc<FaultPlan>) {
+        self.state().fault_plan = Some(plan);
+    }
+
+    // ───────────────────────────────────────────────────────────────────────────
+    // Recording
+    // ───────────────────────────────────────────────────────────────────────────
+
+    /// Every request made so far, in order.
+    #[must_use]
+    pub fn calls(&self) -> Vec<RecordedCall> {
+        self.state().calls.clone()
+    }
+
+    /// The requests made to `method` so far, in order.
+    #[must_use]
+    pub fn calls_to(&self, method: Method) -> Vec<RecordedCall> {
+        self.state()
+            .calls
+            .iter()
+            .filter(|call| call.method() == method)
+            .cloned()
+            .collect()
+    }
+
+    /// Raw transactions sent so far, including those that failed to send.
+    #[must_use]
+    pub fn sent_transactions(&self) -> Vec<Bytes> {
+        self.state().sent_raw.clone()
+    }
+
+    /// Forget the requests recorded so far.
+    pub fn clear_calls(&self) {
+        let mut state = self.state();
+        state.calls.clear();
+        state.sent_raw.clear();
+    }
+
+    // ───────────────────────────────────────────────────────────────────────────
+    // Serving
+    // ───────────────────────────────────────────────────────────────────────────
+
+    /// Record `call`, wait out its method's latency and pop its script.
+    async fn serve(&self, call: RecordedCall) -> Option<Result<Response>> {
+        let (latency, scripted) = self.record(call);
+        if let Some(latency) = latency {
+            tokio::time::sleep(latency).await;
+        }
+        scripted
+    }
+
+    /// Record `call`, returning its method's latency and script.
+    ///
+    /// Without a script, the fault plan's error is returned as one; a
+    /// reverting send reverts like after
+    /// [`revert_next_transaction`](Self::revert_next_transaction).
+    fn record(&self, call: RecordedCall) -> (Option<Duration>, Option<Result<Response>>) {
+        let method = call.method();
+        let mut state = self.state();
+        let raw = match &call {
+            RecordedCall::SendRawTransaction(raw) => {
+                state.sent_raw.push(raw.clone());
+                Some(raw.clone())
+            }
+            _ => None,
+        };
+        state.calls.push(call);
+        let mut latency = state.latency.get(&method).copied();
+        let scripted = state.scripts.get_mut(&method).and_then(VecDeque::pop_front);
+        if scripted.is_some() {
+            return (latency, scripted);
+        }
+        let Some(plan) = state.fault_plan.clone() else {
+            return (latency, None);
+        };
+        let injection = plan.inject(method, raw.as_ref());
+        if !injection.latency.is_zero() {
+            latency = Some(latency.unwrap_or_default() + injection.latency);
+        }
+        state.revert_next |= injection.revert;
+        drop(state);
+        (latency, injection.error.map(Err))
+    }
+
+    /// Receipt of a mined transaction nothing was set up for.
+    fn default_receipt(&self, tx_hash: TxHash, reverted: bool) -> TransactionReceipt {
+        TransactionReceipt {
+            tx_hash,
+            block_hash: B256::ZERO,
+            block_number: self.state().block_number,
+            tx_index: 0,
+            from: Address::ZERO,
+            to: None,
+            contract_address: None,
+            gas_used: DEFAULT_GAS_USED,
+            success: !reverted,
+            logs: Vec::new(),
+        }
+    }
+}
+
+/// Unwrap a scripted response of the expected variant.
+macro_rules! scripted {
+    ($scripted:expr, $variant:ident) => {
+        match $scripted {
+            Some(Ok(Response::$variant(value))) => return Ok(value),
+            Some(Err(e)) => return Err(e),
+            Some(Ok(other)) => {
+                unreachable!("scripted {other:?}, expected {}", stringify!($variant))
+            }
+            None => {}
+        }
+    };
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// CHAIN PROVIDER IMPLEMENTATION
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[async_trait]
+impl ChainProvider for MockChainProvider {
+    fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    async fn get_chain_id(&self) -> Result<u64> {
+        scripted!(self.serve(RecordedCall::ChainId).await, ChainId);
+        Ok(self.chain_id)
+    }
+
+    async fn get_balance(&self, address: Address) -> Result<U256> {
+        scripted!(self.serve(RecordedCall::Balance(address)).await, Amount);
+        Ok(self
+            .state()
+            .balances
+            .get(&address)
+            .copied()
+            .unwrap_or_default())
+    }
+
+    async fn get_nonce(&self, address: Address) -> Result<u64> {
+        scripted!(self.serve(RecordedCall::Nonce(address)).await, Nonce);
+        Ok(self
+            .state()
+            .nonces
+            .get(&address)
+            .copied()
+            .unwrap_or_default())
+    }
+
+    async fn get_token_balance(&self, token: Address, account: Address) -> Result<U256> {
+        if self.token_calls.load(Ordering::Relaxed) {
+            let data = alloy::primitives::hex!("70a08231")
+                .into_iter()
+                .chain(account.into_word())
+                .collect::<Vec<_>>();
+            let request = TransactionRequest::new().to(token).data(Bytes::from(data));
+            let result = self.call(&request).await?;
+            return if result.len() < 32 {
+                Err(ProviderError::InvalidResponse(format!(
+                    "balanceOf({account}) on {token} returned {} bytes",
+                    result.len()
+                )))
+            } else {
+                Ok(U256::from_be_slice(&result[..32]))
+            };
+        }
+        scripted!(
+            self.serve(RecordedCall::TokenBalance { token, account })
+                .await,
+            Amount
+        );
+        Ok(self
+            .state()
+            .token_balances
+            .get(&(token, account))
+            .copied()
+            .unwrap_or_default())
+    }
+
+    async fn send_raw_transaction(&self, tx: Bytes) -> Result<TxHash> {
+        let tx_hash = Self::tx_hash_of(&tx);
+        scripted!(
+            self.serve(RecordedCall::SendRawTransaction(tx)).await,
+            TxHash
+        );
+        let mut state = self.state();
+        let reverted = std::mem::take(&mut state.revert_next);
+        if std::mem::take(&mut state.drop_next) {
+            state.dropped.insert(tx_hash);
+        }
+        state.sent.insert(tx_hash, reverted);
+        drop(state);
+        Ok(tx_hash)
+    }
+
+    async fn wait_for_receipt(
+        &self,
+        tx_hash: TxHash,
+        timeout: Duration,
+    ) -> Result<TransactionReceipt> {
+        match self.serve(RecordedCall::WaitForReceipt(tx_hash)).await {
+            Some(Ok(Response::Receipt(receipt))) => return Ok(*receipt),
+            Some(Err(e)) => return Err(e),
+            Some(Ok(other)) => unreachable!("scripted {other:?}, expected Receipt"),
+            None => {}
+        }
+
+        let (receipt, reverted, dropped) = {
+            let state = self.state();
+            (
+                state.receipts.get(&tx_hash).cloned(),
+                state.sent.get(&tx_hash).copied().unwrap_or_default(),
+                state.dropped.contains(&tx_hash),
+            )
+        };
+        if dropped {
+            return Err(ProviderError::Timeout(timeout));
+        }
+        let mut receipt = receipt.unwrap_or_else(|| self.default_receipt(tx_hash, reverted));
+        if reverted {
+            // A reverted transaction emits nothing
+            receipt.success = false;
+            receipt.logs.clear();
+        }
+        Ok(receipt)
+    }
+
+    async fn estimate_gas(&self, tx: &TransactionRequest) -> Result<u64> {
+        scripted!(self.serve(RecordedCall::EstimateGas(tx.clone())).await, Gas);
+        Ok(100_000)
+    }
+
+    async fn gas_price(&self) -> Result<u128> {
+        scripted!(self.serve(RecordedCall::GasPrice).await, GasPrice);
+        Ok(self.state().gas_price)
+    }
+
+    async fn get_block_number(&self) -> Result<u64> {
+        scripted!(self.serve(RecordedCall::BlockNumber).await, BlockNumber);
+        Ok(self.state().block_number)
+    }
+
+    async fn call(&self, tx: &TransactionRequest) -> Result<Bytes> {
+        scripted!(self.serve(RecordedCall::Call(tx.clone())).await, Data);
+        let (Some(to), Some(data)) = (tx.to, tx.data.as_ref()) else {
+            return Ok(Bytes::new());
+        };
+        let Some(selector) = data.get(..4).and_then(|s| <[u8; 4]>::try_from(s).ok()) else {
+            return Ok(Bytes::new());
+        };
+        match self.state().call_results.get(&(to, selector)) {
+            Some(CallResult::Data(data)) => Ok(data.clone()),
+            Some(CallResult::Revert(reason)) => Err(ProviderError::Rpc {
+                code: 3,
+                message: format!("execution reverted: {reason}"),
+            }),
+            None => Ok(Bytes::new()),
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fault::Fault;
+
+    const TOKEN: Address = Address::repeat_byte(0xAA);
+    const ACCOUNT: Address = Address::repeat_byte(0x01);
+
+    #[tokio::test]
+    async fn serves_state_and_records_calls() {
+        let chain = MockChainProvider::new();
+        chain.set_balance(ACCOUNT, U256::from(7));
+        chain.set_nonce(ACCOUNT, 3);
+        chain.set_token_balance(TOKEN, ACCOUNT, U256::from(100));
+
+        assert_eq!(chain.get_balance(ACCOUNT).await.unwrap(), U256::from(7));
+        assert_eq!(chain.get_nonce(ACCOUNT).await.unwrap(), 3);
+        assert_eq!(chain.get_pending_nonce(ACCOUNT).await.unwrap(), 3);
+        assert_eq!(
+            chain.get_token_balance(TOKEN, ACCOUNT).await.unwrap(),
+            U256::from(100)
+        );
+
+        let methods: Vec<_> = chain.calls().iter().map(RecordedCall::method).collect();
+        assert_eq!(
+            methods,
+            [
+                Method::Balance,
+                Method::Nonce,
+                Method::Nonce,
+                Method::TokenBalance
+            ]
+        );
+        chain.clear_calls();
+        assert!(chain.calls().is_empty());
+    }
+
+    #[tokio::test]
+    async fn scripted_responses_come_first() {
+        let chain = MockChainProvider::new();
+        chain.set_block_number(5);
+        chain.push_response(Method::BlockNumber, Ok(Response::BlockNumber(9)));
+        chain.fail_next(
+            Method::BlockNumber,
+            ProviderError::RateLimited("slow down".into()),
+        );
+
+        assert_eq!(chain.get_block_number().await.unwrap(), 9);
+        assert!(matches!(
+            chain.get_block_number().await,
+            Err(ProviderError::RateLimited(_))
+        ));
+        assert_eq!(chain.get_block_number().await.unwrap(), 5);
+    }
+
+    #[tokio::test]
+    async fn call_results_by_selector() {
+        let chain = MockChainProvider::new();
+        let selector = [0x12, 0x34, 0x56, 0x78];
+        chain.set_call_result(TOKEN, selector, Bytes::from_static(b"ok"));
+        chain.revert_call(TOKEN, [0xde, 0xad, 0xbe, 0xef], "nope");
+
+        let request = |selector: [u8; 4]| {
+            TransactionRequest::new()
+                .to(TOKEN)
+                .data(Bytes::from([selector.as_slice(), b"args"].concat()))
+        };
+        assert_eq!(
+            chain.call(&request(selector)).await.unwrap(),
+            Bytes::from_static(b"ok")
+        );
+        let err = chain
+            .call(&request([0xde, 0xad, 0xbe, 0xef]))
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("execution reverted: nope"),
+            "{err}"
+        );
+        assert!(chain.call(&request([0; 4])).await.unwrap().is_empty());
+
+        let calls = chain.calls_to(Method::Call);
+        assert_eq!(calls.len(), 3);
+        let RecordedCall::Call(first) = &calls[0] else {
+            unreachable!()
+        };
+        assert_eq!(
+            first.data.as_ref().map(|d| d[..4].to_vec()),
+            Some(selector.to_vec())
+        );
+    }
+
+    #[tokio::test]
+    async fn token_balances_by_call() {
+        let chain = MockChainProvider::new();
+        chain.read_token_balances_by_call(true);
+        chain.set_call_result(
+            TOKEN,
+            [0x70, 0xa0, 0x82, 0x31],
+            U256::from(42).to_be_bytes_vec(),
+        );
+
+        assert_eq!(
+            chain.get_token_balance(TOKEN, ACCOUNT).await.unwrap(),
+            U256::from(42)
+        );
+        let RecordedCall::Call(request) = &chain.calls()[0] else {
+            unreachable!("balance read through a call")
+        };
+        assert_eq!(request.data.as_ref().map(|data| data.len()), Some(36));
+    }
+
+    #[tokio::test]
+    async fn transactions_mine_revert_or_drop() {
+        let chain = MockChainProvider::new();
+        let raw = |n: u8| Bytes::from(vec![n]);
+        let timeout = Duration::from_secs(1);
+
+        let mined = chain.send_raw_transaction(raw(1)).await.unwrap();
+        chain.revert_next_transaction();
+        let reverted = chain.send_raw_transaction(raw(2)).await.unwrap();
+        chain.drop_next_transaction();
+        let dropped = chain.send_raw_transaction(raw(3)).await.unwrap();
+
+        let receipt = chain.wait_for_receipt(mined, timeout).await.unwrap();
+        assert!(receipt.success);
+        assert_eq!(receipt.block_number, DEFAULT_BLOCK_NUMBER);
+        assert!(
+            !chain
+                .wait_for_receipt(reverted, timeout)
+                .await
+                .unwrap()
+                .success
+        );
+        assert!(matches!(
+            chain.wait_for_receipt(dropped, timeout).await,
+            Err(ProviderError::Timeout(_))
+        ));
+        assert_eq!(chain.sent_transactions(), [raw(1), raw(2), raw(3)]);
+    }
+
+    #[tokio::test]
+    async fn receipts_by_hash() {
+        let chain = MockChainProvider::new();
+        let raw = Bytes::from_static(b"tx");
+        let log = Log::default();
+        chain.set_receipt_logs(&raw, vec![log.clone()]);
+
+        let tx_hash = chain.send_raw_transaction(raw.clone()).await.unwrap();
+        let receipt = chain
+            .wait_for_receipt(tx_hash, Duration::ZERO)
+            .await
+            .unwrap();
+        assert_eq!(receipt.logs, [log]);
+
+        // Reverting drops the logs
+        chain.revert_next_transaction();
+        chain.send_raw_transaction(raw).await.unwrap();
+        let receipt = chain
+            .wait_for_receipt(tx_hash, Duration::ZERO)
+            .await
+            .unwrap();
+        assert!(!receipt.success && receipt.logs.is_empty());
+    }
+
+    #[tokio::test]
+    async fn fault_plans_fill_in_behind_scripts() {
+        let chain = MockChainProvider::new();
+        let plan = Arc::new(
+            FaultPlan::new(0)
+                .fail_next(Method::SendRawTransaction, 2, Fault::Timeout)
+                .revert_matching(|raw| raw.starts_with(b"extract")),
+        );
+        chain.set_fault_plan(Arc::clone(&plan));
+        chain.push_response(
+            Method::SendRawTransaction,
+            Ok(Response::TxHash(TxHash::repeat_byte(1))),
+        );
+        let raw = Bytes::from_static(b"extract");
+
+        // The script is served first, then the plan's two timeouts
+        assert_eq!(
+            chain.send_raw_transaction(raw.clone()).await.unwrap(),
+            TxHash::repeat_byte(1)
+        );
+        for _ in 0..2 {
+            assert!(matches!(
+                chain.send_raw_transaction(raw.clone()).await,
+                Err(ProviderError::Timeout(_))
+            ));
+        }
+        let tx_hash = chain.send_raw_transaction(raw).await.unwrap();
+        let receipt = chain
+            .wait_for_receipt(tx_hash, Duration::ZERO)
+            .await
+            .unwrap();
+        assert!(!receipt.success);
+
+        // Scripted responses bypass the plan
+        assert_eq!(plan.calls(Method::SendRawTransaction), 3);
+        assert_eq!(plan.faults(Method::SendRawTransaction), 3);
+        assert_eq!(chain.calls_to(Method::SendRawTransaction).len(), 4);
+    }
+
+    #[tokio::test]
+    async fn latency_is_injected() {
+        let chain = MockChainProvider::new();
+        let latency = Duration::from_millis(50);
+        chain.set_latency(Method::GasPrice, latency);
+
+        let started = std::time::Instant::now();
+        chain.gas_price().await.unwrap();
+        assert!(started.elapsed() >= latency);
+
+        let started = std::time::Instant::now();
+        chain.get_block_number().await.unwrap();
+        assert!(started.elapsed() < latency);
+    }
+}
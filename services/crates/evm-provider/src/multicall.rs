@@ -0,0 +1,171 @@
+//! Batched reads through Multicall3.
+//!
+//! [Multicall3](https://github.com/mds1/multicall) is deployed at the same
+//! address on virtually every EVM chain. Its `aggregate3` function executes a
+//! list of calls in one `eth_call` and reports success per call, so a single
+//! failing read does not fail the batch.
+//!
+//! Providers opt in via [`ChainProvider::supports_multicall`]; the default
+//! [`ChainProvider::get_token_balances`] then routes through
+//! [`token_balances`].
+
+use alloy::primitives::{Address, Bytes, U256, address};
+use alloy::sol;
+use alloy::sol_types::SolCall;
+
+use crate::error::{ProviderError, Result};
+use crate::traits::ChainProvider;
+use crate::types::TransactionRequest;
+
+/// Canonical Multicall3 deployment address.
+pub const MULTICALL3_ADDRESS: Address = address!("cA11bde05977b3631167028862bE2a173976CA11");
+
+sol! {
+    /// Multicall3 call with per-call failure handling.
+    struct Call3 {
+        address target;
+        bool allowFailure;
+        bytes callData;
+    }
+
+    /// Outcome of a single [`Call3`].
+    struct Result3 {
+        bool success;
+        bytes returnData;
+    }
+
+    function aggregate3(Call3[] calldata calls) external payable returns (Result3[] memory returnData);
+
+    function balanceOf(address account) external view returns (uint256);
+}
+
+/// Encode an `aggregate3` call reading `balanceOf(account)` on each token.
+#[must_use]
+pub fn encode_token_balances(queries: &[(Address, Address)]) -> Bytes {
+    let calls = queries
+        .iter()
+        .map(|&(token, account)| Call3 {
+            target: token,
+            allowFailure: true,
+            callData: balanceOfCall { account }.abi_encode().into(),
+        })
+        .collect();
+    aggregate3Call { calls }.abi_encode().into()
+}
+
+/// Decode the result of [`encode_token_balances`].
+///
+/// Reads that reverted or returned malformed data come back as `None`.
+///
+/// # Errors
+///
+/// Returns an error if the response is not a valid `aggregate3` result or
+/// holds a different number of results than `expected`.
+pub fn decode_token_balances(data: &[u8], expected: usize) -> Result<Vec<Option<U256>>> {
+    let results = aggregate3Call::abi_decode_returns(data)
+        .map_err(|e| ProviderError::InvalidResponse(format!("malformed aggregate3 result: {e}")))?;
+
+    if results.len() != expected {
+        return Err(ProviderError::InvalidResponse(format!(
+            "aggregate3 returned {} results, expected {expected}",
+            results.len()
+        )));
+    }
+
+    Ok(results
+        .into_iter()
+        .map(|result| {
+            (result.success && result.returnData.len() >= 32)
+                .then(|| U256::from_be_slice(&result.returnData[..32]))
+        })
+        .collect())
+}
+
+/// Read ERC20 balances for `(token, account)` pairs in one `eth_call`.
+///
+/// # Errors
+///
+/// Returns an error if the call to Multicall3 fails (e.g. it is not deployed
+/// on this chain) or returns malformed data.
+pub async fn token_balances<P: ChainProvider + ?Sized>(
+    provider: &P,
+    queries: &[(Address, Address)],
+) -> Result<Vec<Option<U256>>> {
+    if queries.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let request = TransactionRequest::new()
+        .to(MULTICALL3_ADDRESS)
+        .data(encode_token_balances(queries));
+    let result = provider.call(&request).await?;
+
+    if result.is_empty() {
+        return Err(ProviderError::InvalidResponse(format!(
+            "aggregate3 on {MULTICALL3_ADDRESS} returned no data - \
+             Multicall3 may not be deployed on this chain"
+        )));
+    }
+
+    decode_token_balances(&result, queries.len())
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+mod tests {
+    use alloy::sol_types::SolValue;
+
+    use super::*;
+
+    #[test]
+    fn encodes_one_call_per_query() {
+        let token = Address::repeat_byte(0xaa);
+        let account = Address::repeat_byte(0xbb);
+
+        let data = encode_token_balances(&[(token, account), (token, Address::ZERO)]);
+
+        let decoded = aggregate3Call::abi_decode(&data).unwrap();
+        assert_eq!(decoded.calls.len(), 2);
+        assert_eq!(decoded.calls[0].target, token);
+        assert!(decoded.calls[0].allowFailure);
+        assert_eq!(
+            decoded.calls[0].callData,
+            Bytes::from(balanceOfCall { account }.abi_encode())
+        );
+    }
+
+    #[test]
+    fn decodes_failed_reads_as_none() {
+        let results = vec![
+            Result3 {
+                success: true,
+                returnData: U256::from(7).abi_encode().into(),
+            },
+            Result3 {
+                success: false,
+                returnData: Bytes::new(),
+            },
+            Result3 {
+                success: true,
+                returnData: Bytes::from(vec![0u8; 16]),
+            },
+        ];
+        let data = results.abi_encode();
+
+        let balances = decode_token_balances(&data, 3).unwrap();
+
+        assert_eq!(balances, [Some(U256::from(7)), None, None]);
+    }
+
+    #[test]
+    fn rejects_result_count_mismatch() {
+        let data = Vec::<Result3>::new().abi_encode();
+
+        let err = decode_token_balances(&data, 1).unwrap_err();
+
+        assert!(err.to_string().contains("expected 1"), "error: {err}");
+    }
+}
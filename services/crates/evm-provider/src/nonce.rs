@@ -20,16 +20,37 @@
 //! // If transaction fails, resync
 //! nonce_manager.sync(address).await?;
 //! ```
+//!
+//! # Pending Transactions
+//!
+//! A handed-out nonce says nothing about whether its transaction was mined.
+//! [`PendingTracker`] (one comes with every [`LocalNonceManager`]) records
+//! sent transactions until they confirm, so stuck ones can be found:
+//!
+//! ```ignore
+//! let tx_hash = provider.send_raw_transaction(signed).await?;
+//! nonce_manager.pending().register_pending(address, nonce, tx_hash).await;
+//!
+//! // After wait_for_receipt, or let a background task poll receipts
+//! nonce_manager.pending().confirm(address, nonce).await;
+//!
+//! for tx in nonce_manager.pending().stale_pending(Duration::from_secs(120)).await {
+//!     // Resync, replace or alert
+//! }
+//! ```
 
-use std::collections::HashMap;
-use std::sync::Arc;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Weak};
+use std::time::Duration;
 
-use alloy::primitives::Address;
+use alloy::primitives::{Address, TxHash};
 use async_trait::async_trait;
 use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
 use tracing::{debug, warn};
 
-use crate::error::Result;
+use crate::error::{ProviderError, Result};
 use crate::traits::{ChainProvider, NonceManager};
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -65,6 +86,7 @@ use crate::traits::{ChainProvider, NonceManager};
 pub struct LocalNonceManager<P> {
     provider: Arc<P>,
     nonces: RwLock<HashMap<Address, u64>>,
+    pending: PendingTracker,
 }
 
 impl<P: ChainProvider> LocalNonceManager<P> {
@@ -76,6 +98,7 @@ impl<P: ChainProvider> LocalNonceManager<P> {
         Self {
             provider: Arc::new(provider),
             nonces: RwLock::new(HashMap::new()),
+            pending: PendingTracker::default(),
         }
     }
 
@@ -86,9 +109,15 @@ impl<P: ChainProvider> LocalNonceManager<P> {
         Self {
             provider,
             nonces: RwLock::new(HashMap::new()),
+            pending: PendingTracker::default(),
         }
     }
 
+    /// Transactions sent with handed-out nonces that have not confirmed yet.
+    pub const fn pending(&self) -> &PendingTracker {
+        &self.pending
+    }
+
     /// Check if we have a cached nonce for the given address.
     pub async fn has_cached(&self, address: Address) -> bool {
         self.nonces.read().await.contains_key(&address)
@@ -232,6 +261,236 @@ impl<P: ChainProvider> NonceManager for LocalNonceManager<P> {
     }
 }
 
+// ═══════════════════════════════════════════════════════════════════════════════
+// PENDING TRACKER
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Default cap on the pending transactions tracked per address.
+pub const DEFAULT_MAX_PENDING: usize = 64;
+
+/// A sent transaction that has not confirmed yet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PendingTx {
+    /// The sending address.
+    pub address: Address,
+    /// Nonce the transaction was sent with.
+    pub nonce: u64,
+    /// Hash of the transaction last sent with this nonce.
+    pub tx_hash: TxHash,
+    /// When a transaction was first sent with this nonce.
+    pub sent_at: Instant,
+}
+
+impl PendingTx {
+    /// How long the transaction has been pending.
+    #[must_use]
+    pub fn age(&self) -> Duration {
+        self.sent_at.elapsed()
+    }
+}
+
+/// Tracks sent transactions until they confirm.
+///
+/// Confirmation is driven either by the caller after
+/// [`wait_for_receipt`](ChainProvider::wait_for_receipt), or by
+/// [`poll_receipts`](Self::poll_receipts), which checks every pending hash
+/// at once and can run in the background via
+/// [`spawn_receipt_poller`](Self::spawn_receipt_poller).
+///
+/// Tracking is bounded: confirmed entries are dropped, and at most
+/// `max_per_address` transactions are tracked per address, the lowest
+/// nonces being evicted (with a warning) to make room.
+///
+/// # Thread Safety
+///
+/// All operations are thread-safe, like those of [`LocalNonceManager`].
+#[derive(Debug)]
+pub struct PendingTracker {
+    max_per_address: usize,
+    pending: RwLock<HashMap<Address, BTreeMap<u64, PendingTx>>>,
+}
+
+impl Default for PendingTracker {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_PENDING)
+    }
+}
+
+impl PendingTracker {
+    /// Create a tracker keeping at most `max_per_address` pending
+    /// transactions per address (at least one).
+    #[must_use]
+    pub fn new(max_per_address: usize) -> Self {
+        Self {
+            max_per_address: max_per_address.max(1),
+            pending: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Record that `tx_hash` was sent from `address` with `nonce`.
+    ///
+    /// Registering a nonce again (e.g. for a replacement transaction) tracks
+    /// the new hash but keeps the time the nonce was first sent, so a stuck
+    /// nonce stays stale however often it is replaced.
+    pub async fn register_pending(&self, address: Address, nonce: u64, tx_hash: TxHash) {
+        let mut pending = self.pending.write().await;
+        let txs = pending.entry(address).or_default();
+        if let Some(tx) = txs.get_mut(&nonce) {
+            debug!(%address, nonce, %tx_hash, old_hash = %tx.tx_hash, "Replaced pending tx");
+            tx.tx_hash = tx_hash;
+            return;
+        }
+
+        while txs.len() >= self.max_per_address {
+            let Some((evicted, tx)) = txs.pop_first() else {
+                break;
+            };
+            warn!(
+                %address,
+                nonce = evicted,
+                tx_hash = %tx.tx_hash,
+                max = self.max_per_address,
+                "Too many pending transactions, no longer tracking the oldest"
+            );
+        }
+        txs.insert(
+            nonce,
+            PendingTx {
+                address,
+                nonce,
+                tx_hash,
+                sent_at: Instant::now(),
+            },
+        );
+        drop(pending);
+        debug!(%address, nonce, %tx_hash, "Tracking pending tx");
+    }
+
+    /// Record that the transaction with `nonce` from `address` confirmed,
+    /// returning it if it was tracked.
+    ///
+    /// Lower nonces of the address are dropped as well: a nonce cannot be
+    /// mined before the ones below it, so confirmations arriving out of
+    /// order settle the same way.
+    pub async fn confirm(&self, address: Address, nonce: u64) -> Option<PendingTx> {
+        let mut pending = self.pending.write().await;
+        let txs = pending.get_mut(&address)?;
+        let mut rest = txs.split_off(&(nonce + 1));
+        std::mem::swap(txs, &mut rest);
+        let confirmed = rest.remove(&nonce);
+        if !rest.is_empty() {
+            debug!(%address, nonce, settled = rest.len(), "Settled lower pending nonces");
+        }
+        if txs.is_empty() {
+            pending.remove(&address);
+        }
+        confirmed
+    }
+
+    /// Pending transactions of `address`, by nonce.
+    pub async fn pending_for(&self, address: Address) -> Vec<PendingTx> {
+        self.pending
+            .read()
+            .await
+            .get(&address)
+            .map(|txs| txs.values().copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// Pending transactions of every address sent at least `max_age` ago,
+    /// oldest first.
+    pub async fn stale_pending(&self, max_age: Duration) -> Vec<PendingTx> {
+        let mut stale: Vec<PendingTx> = self
+            .pending
+            .read()
+            .await
+            .values()
+            .flat_map(BTreeMap::values)
+            .filter(|tx| tx.age() >= max_age)
+            .copied()
+            .collect();
+        stale.sort_by_key(|tx| tx.sent_at);
+        stale
+    }
+
+    /// Number of pending transactions across all addresses.
+    pub async fn pending_count(&self) -> usize {
+        self.pending.read().await.values().map(BTreeMap::len).sum()
+    }
+
+    /// Check the receipts of all pending transactions at once, confirming
+    /// those that were mined, and return how many were.
+    ///
+    /// Each receipt is waited for up to `timeout`. Reverted transactions
+    /// confirm too, as they used their nonce.
+    pub async fn poll_receipts<P: ChainProvider + ?Sized>(
+        &self,
+        provider: &P,
+        timeout: Duration,
+    ) -> usize {
+        let txs: Vec<PendingTx> = self
+            .pending
+            .read()
+            .await
+            .values()
+            .flat_map(BTreeMap::values)
+            .copied()
+            .collect();
+        let receipts = futures::future::join_all(
+            txs.iter()
+                .map(|tx| provider.wait_for_receipt(tx.tx_hash, timeout)),
+        )
+        .await;
+
+        let mut confirmed = 0;
+        for (tx, receipt) in txs.iter().zip(receipts) {
+            match receipt {
+                Ok(_) => {
+                    // The nonce may have been replaced since it was polled
+                    if self.pending_hash(tx.address, tx.nonce).await == Some(tx.tx_hash) {
+                        self.confirm(tx.address, tx.nonce).await;
+                        confirmed += 1;
+                    }
+                }
+                Err(ProviderError::ReceiptNotFound(_)) => {}
+                Err(e) => {
+                    debug!(tx_hash = %tx.tx_hash, error = %e, "Error polling receipt");
+                }
+            }
+        }
+        confirmed
+    }
+
+    /// Run [`poll_receipts`](Self::poll_receipts) every `interval` in a
+    /// background task.
+    ///
+    /// The task ends on its own once the tracker is dropped.
+    pub fn spawn_receipt_poller<P: ChainProvider + 'static>(
+        self: &Arc<Self>,
+        provider: Arc<P>,
+        interval: Duration,
+        timeout: Duration,
+    ) -> JoinHandle<()> {
+        let tracker: Weak<Self> = Arc::downgrade(self);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                let Some(tracker) = tracker.upgrade() else {
+                    break;
+                };
+                tracker.poll_receipts(provider.as_ref(), timeout).await;
+            }
+        })
+    }
+
+    async fn pending_hash(&self, address: Address, nonce: u64) -> Option<TxHash> {
+        let pending = self.pending.read().await;
+        pending.get(&address)?.get(&nonce).map(|tx| tx.tx_hash)
+    }
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // TESTS
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -242,14 +501,17 @@ mod tests {
     use crate::traits::ChainProvider;
     use crate::types::{TransactionReceipt, TransactionRequest};
     use alloy::primitives::{Bytes, TxHash, B256, U256};
+    use std::collections::HashSet;
+    use std::sync::Mutex;
     use std::sync::atomic::{AtomicU64, Ordering};
-    use std::time::Duration;
 
     /// Mock provider that tracks nonce queries
     #[derive(Debug)]
     struct MockProvider {
         chain_nonce: AtomicU64,
         query_count: AtomicU64,
+        /// Hashes that have no receipt
+        unmined: Mutex<HashSet<TxHash>>,
     }
 
     impl MockProvider {
@@ -257,9 +519,14 @@ mod tests {
             Self {
                 chain_nonce: AtomicU64::new(initial_nonce),
                 query_count: AtomicU64::new(0),
+                unmined: Mutex::new(HashSet::new()),
             }
         }
 
+        fn set_unmined(&self, tx_hash: TxHash) {
+            self.unmined.lock().unwrap().insert(tx_hash);
+        }
+
         fn set_chain_nonce(&self, nonce: u64) {
             self.chain_nonce.store(nonce, Ordering::SeqCst);
         }
@@ -297,6 +564,9 @@ mod tests {
             tx_hash: TxHash,
             _timeout: Duration,
         ) -> Result<TransactionReceipt> {
+            if self.unmined.lock().unwrap().contains(&tx_hash) {
+                return Err(crate::error::ProviderError::ReceiptNotFound(tx_hash));
+            }
             Ok(TransactionReceipt {
                 tx_hash,
                 block_hash: B256::ZERO,
@@ -580,4 +850,121 @@ mod tests {
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("simulated failure"));
     }
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Pending transactions
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    fn nonces(txs: &[PendingTx]) -> Vec<u64> {
+        txs.iter().map(|tx| tx.nonce).collect()
+    }
+
+    #[tokio::test]
+    async fn confirmations_settle_out_of_order() {
+        let tracker = PendingTracker::default();
+        let addr = Address::repeat_byte(0x0b);
+        for nonce in 3..=5u8 {
+            tracker
+                .register_pending(addr, nonce.into(), TxHash::repeat_byte(nonce))
+                .await;
+        }
+
+        // Nonce 4 confirming means nonce 3 was mined too
+        let confirmed = tracker.confirm(addr, 4).await.unwrap();
+        assert_eq!(confirmed.tx_hash, TxHash::repeat_byte(4));
+        assert_eq!(nonces(&tracker.pending_for(addr).await), vec![5]);
+
+        // Its late confirmation is a no-op
+        assert!(tracker.confirm(addr, 3).await.is_none());
+        assert_eq!(nonces(&tracker.pending_for(addr).await), vec![5]);
+
+        tracker.confirm(addr, 5).await.unwrap();
+        assert!(tracker.pending_for(addr).await.is_empty());
+        assert_eq!(tracker.pending_count().await, 0);
+    }
+
+    #[tokio::test]
+    async fn oldest_pending_is_evicted_at_cap() {
+        let tracker = PendingTracker::new(2);
+        let addr = Address::repeat_byte(0x0c);
+        let other = Address::repeat_byte(0x0d);
+        for nonce in 1..=3u8 {
+            tracker
+                .register_pending(addr, nonce.into(), TxHash::repeat_byte(nonce))
+                .await;
+        }
+        tracker.register_pending(other, 1, TxHash::ZERO).await;
+        assert_eq!(nonces(&tracker.pending_for(addr).await), vec![2, 3]);
+
+        // A replacement takes no extra room
+        tracker
+            .register_pending(addr, 3, TxHash::repeat_byte(0x33))
+            .await;
+        let pending = tracker.pending_for(addr).await;
+        assert_eq!(nonces(&pending), vec![2, 3]);
+        assert_eq!(pending[1].tx_hash, TxHash::repeat_byte(0x33));
+        assert_eq!(tracker.pending_count().await, 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn stale_pending_by_age() {
+        let tracker = PendingTracker::default();
+        let addr = Address::repeat_byte(0x0e);
+        let max_age = Duration::from_secs(60);
+
+        tracker.register_pending(addr, 1, TxHash::repeat_byte(1)).await;
+        tokio::time::advance(Duration::from_secs(30)).await;
+        tracker.register_pending(addr, 2, TxHash::repeat_byte(2)).await;
+        assert!(tracker.stale_pending(max_age).await.is_empty());
+
+        tokio::time::advance(Duration::from_secs(30)).await;
+        assert_eq!(nonces(&tracker.stale_pending(max_age).await), vec![1]);
+
+        // Replacing a stuck transaction does not make it fresh
+        tracker.register_pending(addr, 1, TxHash::repeat_byte(9)).await;
+        tokio::time::advance(Duration::from_secs(30)).await;
+        assert_eq!(nonces(&tracker.stale_pending(max_age).await), vec![1, 2]);
+
+        tracker.confirm(addr, 1).await;
+        assert_eq!(nonces(&tracker.stale_pending(max_age).await), vec![2]);
+    }
+
+    #[tokio::test]
+    async fn poll_receipts_confirms_mined() {
+        let provider = MockProvider::new(0);
+        let manager = LocalNonceManager::new(provider);
+        let addr = Address::repeat_byte(0x0f);
+        let stuck = TxHash::repeat_byte(0xee);
+        manager.provider.set_unmined(stuck);
+
+        let tracker = manager.pending();
+        tracker.register_pending(addr, 1, TxHash::repeat_byte(1)).await;
+        tracker.register_pending(addr, 2, stuck).await;
+        let other = Address::repeat_byte(0x10);
+        tracker.register_pending(other, 7, TxHash::repeat_byte(2)).await;
+
+        let confirmed = tracker
+            .poll_receipts(manager.provider.as_ref(), Duration::from_secs(1))
+            .await;
+        assert_eq!(confirmed, 2);
+        assert_eq!(nonces(&tracker.pending_for(addr).await), vec![2]);
+        assert!(tracker.pending_for(other).await.is_empty());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn receipt_poller_runs_in_background() {
+        let provider = Arc::new(MockProvider::new(0));
+        let tracker = Arc::new(PendingTracker::default());
+        let addr = Address::repeat_byte(0x11);
+        tracker.register_pending(addr, 0, TxHash::repeat_byte(1)).await;
+
+        let poller =
+            tracker.spawn_receipt_poller(provider, Duration::from_secs(5), Duration::from_secs(1));
+        tokio::time::sleep(Duration::from_secs(6)).await;
+        assert_eq!(tracker.pending_count().await, 0);
+
+        drop(tracker);
+        tokio::time::sleep(Duration::from_secs(6)).await;
+        assert!(poller.is_finished());
+    }
 }
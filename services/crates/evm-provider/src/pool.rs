@@ -0,0 +1,831 @@
+//! Spreading wallets over several RPC endpoints of the same chain.
+//!
+//! This module provides [`ProviderPool`], which assigns each wallet address
+//! one of a list of endpoints, so that a fleet's traffic is spread over
+//! several providers instead of hitting one. Unlike [`FailoverProvider`],
+//! which sends every request to the most preferred healthy endpoint, a pool
+//! hands out a [`PoolEndpoint`] per wallet and the wallet's requests go there.
+//!
+//! # Assignment
+//!
+//! How a wallet's endpoint is chosen follows the pool's
+//! [`AssignmentStrategy`]:
+//!
+//! - `sticky`: the address is hashed against each endpoint name (rendezvous
+//!   hashing). A wallet always lands on the same endpoint, and adding or
+//!   removing one only moves the wallets of that endpoint.
+//! - `weighted`: like `sticky`, with each endpoint taking a share of the
+//!   wallets proportional to its weight.
+//! - `round_robin`: wallets are dealt out in the order they first ask.
+//!
+//! # Health
+//!
+//! An endpoint that fails [`failure_threshold`](ProviderPool::with_failure_threshold)
+//! requests in a row with an error another endpoint could avoid is skipped,
+//! and its wallets are temporarily assigned their next endpoint in line.
+//! After [`retry_after`](ProviderPool::with_retry_after) the endpoint gets
+//! traffic again; the first success brings its wallets back.
+//!
+//! # Nonces
+//!
+//! Endpoints do not always agree on the latest state, so a wallet that moves
+//! to another endpoint may read an older nonce there.
+//! [`ProviderPool::assign`] reports the move, so that the caller can keep
+//! the wallet's nonce from going backwards until the new endpoint caught up.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use evm_provider::pool::{AssignmentStrategy, ProviderPool};
+//!
+//! let pool = ProviderPool::new(
+//!     AssignmentStrategy::Sticky,
+//!     vec![
+//!         ("primary".to_string(), 1, StandardEvmProvider::new("https://carrot.megaeth.com/rpc").await?),
+//!         ("backup".to_string(), 1, StandardEvmProvider::new("https://backup.example/rpc").await?),
+//!     ],
+//! )?;
+//!
+//! let provider = pool.provider_for(wallet.address);
+//! let nonce = provider.get_nonce(wallet.address).await?;
+//! ```
+//!
+//! [`FailoverProvider`]: crate::FailoverProvider
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+use alloy::primitives::{Address, Bytes, TxHash, U256, keccak256};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::error::{ProviderError, Result};
+use crate::traits::{ChainProvider, ExtendedChainProvider};
+use crate::types::{LogFilter, LogsPage, TransactionReceipt, TransactionRequest};
+
+/// Default number of consecutive failures after which an endpoint is skipped.
+const DEFAULT_FAILURE_THRESHOLD: u32 = 3;
+
+/// Default time a skipped endpoint waits before it gets traffic again.
+const DEFAULT_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// ASSIGNMENT STRATEGY
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// How a [`ProviderPool`] assigns wallets to endpoints.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AssignmentStrategy {
+    /// Deal wallets out in the order they first ask.
+    RoundRobin,
+
+    /// Hash each wallet to an endpoint.
+    #[default]
+    Sticky,
+
+    /// Hash each wallet to an endpoint, in proportion to endpoint weights.
+    Weighted,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// ENDPOINT STATS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Traffic and health of one endpoint of a [`ProviderPool`], for metrics.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EndpointStats {
+    /// Name the endpoint was registered under.
+    pub name: String,
+
+    /// Whether wallets are assigned to the endpoint.
+    pub healthy: bool,
+
+    /// Requests the endpoint was sent.
+    pub requests: u64,
+
+    /// Requests the endpoint failed.
+    pub failures: u64,
+
+    /// Wallets currently assigned to the endpoint.
+    pub wallets: usize,
+
+    /// Transactions submitted through the endpoint's realtime API.
+    #[serde(default)]
+    pub realtime_submissions: u64,
+
+    /// Transactions submitted with a plain send.
+    #[serde(default)]
+    pub standard_submissions: u64,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// POOL ENDPOINT
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Failures of an endpoint since it last served a request.
+#[derive(Debug, Default)]
+struct FailureState {
+    /// Failures in a row.
+    consecutive: u32,
+
+    /// When the most recent one happened.
+    last: Option<Instant>,
+}
+
+/// One endpoint of a [`ProviderPool`].
+///
+/// Implements [`ChainProvider`] and [`ExtendedChainProvider`] by passing
+/// requests on to the wrapped provider, counting them and their failures on
+/// the way, and sent transactions by how they were submitted.
+#[derive(Debug)]
+pub struct PoolEndpoint<P> {
+    /// Name for logs and metrics, e.g. the host.
+    name: String,
+
+    /// Share of the wallets under [`AssignmentStrategy::Weighted`].
+    weight: u32,
+
+    /// The wrapped provider.
+    provider: P,
+
+    /// Requests sent.
+    requests: AtomicU64,
+
+    /// Requests failed.
+    failures: AtomicU64,
+
+    /// Transactions submitted through the realtime API.
+    realtime_submissions: AtomicU64,
+
+    /// Transactions submitted with a plain send.
+    standard_submissions: AtomicU64,
+
+    /// Failures since the last success.
+    failure_state: Mutex<FailureState>,
+}
+
+impl<P> PoolEndpoint<P> {
+    /// Name the endpoint was registered under.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The wrapped provider.
+    #[must_use]
+    pub const fn inner(&self) -> &P {
+        &self.provider
+    }
+
+    /// Requests the endpoint was sent.
+    #[must_use]
+    pub fn requests(&self) -> u64 {
+        self.requests.load(Ordering::Relaxed)
+    }
+
+    /// Count a request and its outcome.
+    fn track<T>(&self, result: Result<T>) -> Result<T> {
+        self.requests.fetch_add(1, Ordering::Relaxed);
+        match &result {
+            Ok(_) => {
+                let recovered = {
+                    let mut state = lock(&self.failure_state);
+                    state.last = None;
+                    std::mem::take(&mut state.consecutive) > 0
+                };
+                if recovered {
+                    info!(endpoint = %self.name, "RPC endpoint serving again");
+                }
+            }
+            Err(e) if is_endpoint_failure(e) => {
+                self.failures.fetch_add(1, Ordering::Relaxed);
+                let mut state = lock(&self.failure_state);
+                state.consecutive = state.consecutive.saturating_add(1);
+                state.last = Some(Instant::now());
+            }
+            Err(_) => {}
+        }
+        result
+    }
+
+    /// Whether wallets may be assigned to the endpoint.
+    fn is_available(&self, failure_threshold: u32, retry_after: Duration) -> bool {
+        let state = lock(&self.failure_state);
+        state.consecutive < failure_threshold
+            || state.last.is_none_or(|last| last.elapsed() >= retry_after)
+    }
+
+    /// Rendezvous score of `address` on this endpoint; the highest wins.
+    fn score(&self, address: Address, weighted: bool) -> f64 {
+        let mut key = address.to_vec();
+        key.extend_from_slice(self.name.as_bytes());
+        let hash = keccak256(key);
+        let high = u32::from_be_bytes([hash[0], hash[1], hash[2], hash[3]]);
+        // Uniform in (0, 1), so the logarithm is negative and finite
+        let uniform = (f64::from(high) + 0.5) / 4_294_967_296.0;
+        let weight = if weighted { f64::from(self.weight) } else { 1.0 };
+        -weight / uniform.ln()
+    }
+}
+
+/// Lock a mutex, recovering the data if a holder panicked.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Whether another endpoint might serve a request that failed with `error`.
+///
+/// Timeouts waiting for a receipt say nothing about the endpoint, and
+/// non-retryable errors (reverts, bad input) would fail everywhere.
+const fn is_endpoint_failure(error: &ProviderError) -> bool {
+    error.is_retryable() && !matches!(error, ProviderError::Timeout(_))
+}
+
+#[async_trait]
+impl<P: ChainProvider> ChainProvider for PoolEndpoint<P> {
+    fn chain_id(&self) -> u64 {
+        self.provider.chain_id()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    async fn get_chain_id(&self) -> Result<u64> {
+        self.track(self.provider.get_chain_id().await)
+    }
+
+    async fn get_balance(&self, address: Address) -> Result<U256> {
+        self.track(self.provider.get_balance(address).await)
+    }
+
+    async fn get_nonce(&self, address: Address) -> Result<u64> {
+        self.track(self.provider.get_nonce(address).await)
+    }
+
+    async fn get_pending_nonce(&self, address: Address) -> Result<u64> {
+        self.track(self.provider.get_pending_nonce(address).await)
+    }
+
+    async fn send_raw_transaction(&self, tx: Bytes) -> Result<TxHash> {
+        self.standard_submissions.fetch_add(1, Ordering::Relaxed);
+        self.track(self.provider.send_raw_transaction(tx).await)
+    }
+
+    async fn wait_for_receipt(
+        &self,
+        tx_hash: TxHash,
+        timeout: Duration,
+    ) -> Result<TransactionReceipt> {
+        self.track(self.provider.wait_for_receipt(tx_hash, timeout).await)
+    }
+
+    async fn estimate_gas(&self, tx: &TransactionRequest) -> Result<u64> {
+        self.track(self.provider.estimate_gas(tx).await)
+    }
+
+    async fn gas_price(&self) -> Result<u128> {
+        self.track(self.provider.gas_price().await)
+    }
+
+    async fn get_block_number(&self) -> Result<u64> {
+        self.track(self.provider.get_block_number().await)
+    }
+
+    async fn call(&self, tx: &TransactionRequest) -> Result<Bytes> {
+        self.track(self.provider.call(tx).await)
+    }
+
+    async fn get_token_balance(&self, token: Address, account: Address) -> Result<U256> {
+        self.track(self.provider.get_token_balance(token, account).await)
+    }
+
+    fn supports_multicall(&self) -> bool {
+        self.provider.supports_multicall()
+    }
+
+    async fn get_token_balances(
+        &self,
+        queries: &[(Address, Address)],
+    ) -> Result<Vec<Option<U256>>> {
+        self.track(self.provider.get_token_balances(queries).await)
+    }
+}
+
+#[async_trait]
+impl<P: ExtendedChainProvider> ExtendedChainProvider for PoolEndpoint<P> {
+    fn supports_realtime(&self) -> bool {
+        self.provider.supports_realtime()
+    }
+
+    fn supports_cursor_pagination(&self) -> bool {
+        self.provider.supports_cursor_pagination()
+    }
+
+    async fn send_realtime(&self, tx: Bytes) -> Result<TransactionReceipt> {
+        self.realtime_submissions.fetch_add(1, Ordering::Relaxed);
+        self.track(self.provider.send_realtime(tx).await)
+    }
+
+    async fn get_logs_with_cursor(
+        &self,
+        filter: &LogFilter,
+        cursor: Option<&str>,
+    ) -> Result<LogsPage> {
+        self.track(self.provider.get_logs_with_cursor(filter, cursor).await)
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// PROVIDER POOL
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// An endpoint handed out by [`ProviderPool::assign`].
+#[derive(Debug)]
+pub struct Assigned<P> {
+    /// Endpoint the wallet's requests go to.
+    pub endpoint: Arc<PoolEndpoint<P>>,
+
+    /// Endpoint the wallet was assigned before, if it moved since.
+    pub moved_from: Option<String>,
+}
+
+/// Endpoints of one chain with wallets assigned to them.
+///
+/// See the [module documentation](self) for how wallets are assigned and
+/// reassigned.
+#[derive(Debug)]
+pub struct ProviderPool<P> {
+    /// Endpoints, in registration order.
+    endpoints: Vec<Arc<PoolEndpoint<P>>>,
+
+    /// How wallets are assigned.
+    strategy: AssignmentStrategy,
+
+    /// Consecutive failures after which an endpoint is skipped.
+    failure_threshold: u32,
+
+    /// Time a skipped endpoint waits before it gets traffic again.
+    retry_after: Duration,
+
+    /// Endpoint each wallet was dealt under round robin.
+    homes: Mutex<HashMap<Address, usize>>,
+
+    /// Next endpoint to deal under round robin.
+    next_home: AtomicUsize,
+
+    /// Endpoint each wallet was last assigned.
+    assigned: Mutex<HashMap<Address, usize>>,
+}
+
+impl<P: ChainProvider> ProviderPool<P> {
+    /// Create a pool over `(name, weight, provider)` endpoints.
+    ///
+    /// Weights only matter under [`AssignmentStrategy::Weighted`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProviderError::InvalidConfig`] if no endpoints are given, a
+    /// name is used twice, a weight is zero, or the endpoints do not all
+    /// serve the same chain.
+    pub fn new(strategy: AssignmentStrategy, endpoints: Vec<(String, u32, P)>) -> Result<Self> {
+        let Some((first_name, _, first)) = endpoints.first() else {
+            return Err(ProviderError::InvalidConfig(
+                "provider pool needs at least one endpoint".into(),
+            ));
+        };
+        let chain_id = first.chain_id();
+        for (i, (name, weight, provider)) in endpoints.iter().enumerate() {
+            if provider.chain_id() != chain_id {
+                return Err(ProviderError::InvalidConfig(format!(
+                    "endpoint {name} serves chain {}, but {first_name} serves chain {chain_id}",
+                    provider.chain_id()
+                )));
+            }
+            if *weight == 0 {
+                return Err(ProviderError::InvalidConfig(format!(
+                    "endpoint {name} has weight 0"
+                )));
+            }
+            if endpoints[..i].iter().any(|(other, _, _)| other == name) {
+                return Err(ProviderError::InvalidConfig(format!(
+                    "endpoint name {name} is used twice"
+                )));
+            }
+        }
+
+        Ok(Self::from_endpoints(strategy, endpoints))
+    }
+
+    /// Create a pool of a single endpoint, which gets every wallet.
+    #[must_use]
+    pub fn single(name: impl Into<String>, provider: P) -> Self {
+        Self::from_endpoints(AssignmentStrategy::Sticky, vec![(name.into(), 1, provider)])
+    }
+
+    /// Create a pool over checked endpoints.
+    fn from_endpoints(strategy: AssignmentStrategy, endpoints: Vec<(String, u32, P)>) -> Self {
+        Self {
+            endpoints: endpoints
+                .into_iter()
+                .map(|(name, weight, provider)| {
+                    Arc::new(PoolEndpoint {
+                        name,
+                        weight,
+                        provider,
+                        requests: AtomicU64::new(0),
+                        failures: AtomicU64::new(0),
+                        realtime_submissions: AtomicU64::new(0),
+                        standard_submissions: AtomicU64::new(0),
+                        failure_state: Mutex::new(FailureState::default()),
+                    })
+                })
+                .collect(),
+            strategy,
+            failure_threshold: DEFAULT_FAILURE_THRESHOLD,
+            retry_after: DEFAULT_RETRY_AFTER,
+            homes: Mutex::new(HashMap::new()),
+            next_home: AtomicUsize::new(0),
+            assigned: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Set the number of consecutive failures after which an endpoint is
+    /// skipped.
+    ///
+    /// Default is 3. Zero is treated as one.
+    #[must_use]
+    pub fn with_failure_threshold(mut self, failures: u32) -> Self {
+        self.failure_threshold = failures.max(1);
+        self
+    }
+
+    /// Set how long a skipped endpoint waits before it gets traffic again.
+    ///
+    /// Default is 60 seconds.
+    #[must_use]
+    pub const fn with_retry_after(mut self, retry_after: Duration) -> Self {
+        self.retry_after = retry_after;
+        self
+    }
+
+    /// How wallets are assigned.
+    #[must_use]
+    pub const fn strategy(&self) -> AssignmentStrategy {
+        self.strategy
+    }
+
+    /// The first registered endpoint, for traffic that belongs to no wallet.
+    #[must_use]
+    pub fn primary(&self) -> &Arc<PoolEndpoint<P>> {
+        &self.endpoints[0]
+    }
+
+    /// Every endpoint, in registration order.
+    #[must_use]
+    pub fn endpoints(&self) -> &[Arc<PoolEndpoint<P>>] {
+        &self.endpoints
+    }
+
+    /// Endpoint `address`'s requests go to.
+    #[must_use]
+    pub fn provider_for(&self, address: Address) -> Arc<PoolEndpoint<P>> {
+        self.assign(address).endpoint
+    }
+
+    /// Assign `address` an endpoint, reporting whether it moved.
+    ///
+    /// The wallet gets the first available endpoint in its order of
+    /// preference, or its preferred one if none is available.
+    pub fn assign(&self, address: Address) -> Assigned<P> {
+        let order = self.preference(address);
+        let index = order
+            .iter()
+            .copied()
+            .find(|&i| self.endpoints[i].is_available(self.failure_threshold, self.retry_after))
+            .unwrap_or(order[0]);
+
+        let previous = lock(&self.assigned).insert(address, index);
+        let moved_from = previous
+            .filter(|&previous| previous != index)
+            .map(|previous| self.endpoints[previous].name.clone());
+        if let Some(from) = &moved_from {
+            let to = &self.endpoints[index].name;
+            if index == order[0] {
+                info!(%address, from = %from, to = %to, "Wallet moved back to its RPC endpoint");
+            } else {
+                warn!(
+                    %address,
+                    from = %from,
+                    to = %to,
+                    "Wallet reassigned to another RPC endpoint"
+                );
+            }
+        }
+
+        Assigned {
+            endpoint: Arc::clone(&self.endpoints[index]),
+            moved_from,
+        }
+    }
+
+    /// Name of the endpoint `address` was last assigned, if any.
+    #[must_use]
+    pub fn assigned_endpoint(&self, address: Address) -> Option<&str> {
+        let index = lock(&self.assigned).get(&address).copied()?;
+        Some(&self.endpoints[index].name)
+    }
+
+    /// Traffic and health of every endpoint, in registration order.
+    #[must_use]
+    pub fn endpoint_stats(&self) -> Vec<EndpointStats> {
+        let mut wallets = vec![0; self.endpoints.len()];
+        let assigned = lock(&self.assigned);
+        for &index in assigned.values() {
+            wallets[index] += 1;
+        }
+        drop(assigned);
+
+        self.endpoints
+            .iter()
+            .zip(wallets)
+            .map(|(endpoint, wallets)| EndpointStats {
+                name: endpoint.name.clone(),
+                healthy: endpoint.is_available(self.failure_threshold, self.retry_after),
+                requests: endpoint.requests(),
+                failures: endpoint.failures.load(Ordering::Relaxed),
+                wallets,
+                realtime_submissions: endpoint.realtime_submissions.load(Ordering::Relaxed),
+                standard_submissions: endpoint.standard_submissions.load(Ordering::Relaxed),
+            })
+            .collect()
+    }
+
+    /// Endpoints in `address`'s order of preference.
+    fn preference(&self, address: Address) -> Vec<usize> {
+        let count = self.endpoints.len();
+        match self.strategy {
+            AssignmentStrategy::RoundRobin => {
+                let home = *lock(&self.homes).entry(address).or_insert_with(|| {
+                    self.next_home.fetch_add(1, Ordering::Relaxed) % count
+                });
+                (0..count).map(|offset| (home + offset) % count).collect()
+            }
+            AssignmentStrategy::Sticky | AssignmentStrategy::Weighted => {
+                let weighted = self.strategy == AssignmentStrategy::Weighted;
+                let mut scored: Vec<(f64, usize)> = self
+                    .endpoints
+                    .iter()
+                    .enumerate()
+                    .map(|(i, endpoint)| (endpoint.score(address, weighted), i))
+                    .collect();
+                scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+                scored.into_iter().map(|(_, i)| i).collect()
+            }
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockProvider;
+
+    fn pool(
+        strategy: AssignmentStrategy,
+        endpoints: &[(&str, u32, &Arc<MockProvider>)],
+    ) -> ProviderPool<Arc<MockProvider>> {
+        ProviderPool::new(
+            strategy,
+            endpoints
+                .iter()
+                .map(|(name, weight, provider)| (name.to_string(), *weight, Arc::clone(provider)))
+                .collect(),
+        )
+        .unwrap()
+    }
+
+    fn mocks(count: usize) -> Vec<Arc<MockProvider>> {
+        (0..count).map(|_| Arc::new(MockProvider::new())).collect()
+    }
+
+    fn wallets(count: u8) -> impl Iterator<Item = Address> {
+        (1..=count).map(Address::repeat_byte)
+    }
+
+    #[test]
+    fn sticky_assignment_is_stable() {
+        let providers = mocks(3);
+        let endpoints = [
+            ("a", 1, &providers[0]),
+            ("b", 1, &providers[1]),
+            ("c", 1, &providers[2]),
+        ];
+        let first = pool(AssignmentStrategy::Sticky, &endpoints);
+        let second = pool(AssignmentStrategy::Sticky, &endpoints);
+
+        // The same address lands on the same endpoint, in any pool over the
+        // same endpoints, however often it asks
+        for address in wallets(30) {
+            let name = first.provider_for(address).name().to_string();
+            assert_eq!(first.provider_for(address).name(), name);
+            assert_eq!(second.provider_for(address).name(), name);
+            assert!(first.assign(address).moved_from.is_none());
+        }
+
+        // And the wallets are spread over all of them
+        let stats = first.endpoint_stats();
+        assert!(stats.iter().all(|s| s.wallets > 0), "{stats:?}");
+        assert_eq!(stats.iter().map(|s| s.wallets).sum::<usize>(), 30);
+
+        // Dropping an endpoint only moves the wallets that were on it
+        let without_c = pool(AssignmentStrategy::Sticky, &endpoints[..2]);
+        for address in wallets(30) {
+            let name = first.provider_for(address).name().to_string();
+            if name != "c" {
+                assert_eq!(without_c.provider_for(address).name(), name);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn wallets_of_failing_endpoint_are_reassigned() {
+        let providers = mocks(2);
+        let pool = pool(
+            AssignmentStrategy::Sticky,
+            &[("a", 1, &providers[0]), ("b", 1, &providers[1])],
+        )
+        .with_failure_threshold(2)
+        .with_retry_after(Duration::from_secs(3600));
+        let address = wallets(20)
+            .find(|&address| pool.provider_for(address).name() == "a")
+            .unwrap();
+
+        // One failure is tolerated, the second takes the endpoint out
+        providers[0].set_offline(true);
+        let endpoint = pool.provider_for(address);
+        assert!(endpoint.get_nonce(address).await.is_err());
+        assert_eq!(pool.provider_for(address).name(), "a");
+        assert!(endpoint.get_nonce(address).await.is_err());
+
+        let assigned = pool.assign(address);
+        assert_eq!(assigned.endpoint.name(), "b");
+        assert_eq!(assigned.moved_from.as_deref(), Some("a"));
+        assert_eq!(pool.assigned_endpoint(address), Some("b"));
+        assert!(assigned.endpoint.get_nonce(address).await.is_ok());
+
+        let stats = pool.endpoint_stats();
+        assert!(!stats[0].healthy && stats[1].healthy);
+        assert_eq!((stats[0].requests, stats[0].failures), (2, 2));
+        assert_eq!((stats[1].requests, stats[1].failures), (1, 0));
+    }
+
+    #[tokio::test]
+    async fn recovered_endpoint_gets_its_wallets_back() {
+        let providers = mocks(2);
+        let pool = pool(
+            AssignmentStrategy::Sticky,
+            &[("a", 1, &providers[0]), ("b", 1, &providers[1])],
+        )
+        .with_failure_threshold(1)
+        .with_retry_after(Duration::ZERO);
+        let address = wallets(20)
+            .find(|&address| pool.provider_for(address).name() == "a")
+            .unwrap();
+
+        providers[0].set_offline(true);
+        assert!(pool.provider_for(address).get_balance(address).await.is_err());
+
+        // Without a wait the endpoint is retried right away, and a success
+        // keeps the wallet there
+        providers[0].set_offline(false);
+        let endpoint = pool.provider_for(address);
+        assert_eq!(endpoint.name(), "a");
+        assert!(endpoint.get_balance(address).await.is_ok());
+        assert!(pool.endpoint_stats()[0].healthy);
+    }
+
+    #[tokio::test]
+    async fn request_errors_do_not_count_as_failures() {
+        let providers = mocks(2);
+        providers[0].fail_token_balance(Address::ZERO, Address::ZERO);
+        let pool = pool(
+            AssignmentStrategy::RoundRobin,
+            &[("a", 1, &providers[0]), ("b", 1, &providers[1])],
+        )
+        .with_failure_threshold(1);
+
+        // The first wallet is dealt the first endpoint
+        let endpoint = pool.provider_for(Address::ZERO);
+        assert_eq!(endpoint.name(), "a");
+        assert!(
+            endpoint
+                .get_token_balance(Address::ZERO, Address::ZERO)
+                .await
+                .is_err()
+        );
+        assert_eq!(pool.provider_for(Address::ZERO).name(), "a");
+        assert_eq!(pool.endpoint_stats()[0].failures, 0);
+    }
+
+    #[tokio::test]
+    async fn counts_submissions_by_path() {
+        let providers = mocks(2);
+        providers[0].set_realtime_support(true);
+        let pool = pool(
+            AssignmentStrategy::RoundRobin,
+            &[("a", 1, &providers[0]), ("b", 1, &providers[1])],
+        );
+
+        let realtime = pool.provider_for(Address::ZERO);
+        assert!(realtime.supports_realtime());
+        realtime.send_realtime(Bytes::new()).await.unwrap();
+        realtime.send_raw_transaction(Bytes::new()).await.unwrap();
+        let standard = pool.provider_for(Address::repeat_byte(1));
+        assert!(!standard.supports_realtime());
+        standard.send_raw_transaction(Bytes::new()).await.unwrap();
+
+        let stats = pool.endpoint_stats();
+        let counts: Vec<(u64, u64)> = stats
+            .iter()
+            .map(|s| (s.realtime_submissions, s.standard_submissions))
+            .collect();
+        assert_eq!(counts, [(1, 1), (0, 1)]);
+    }
+
+    #[test]
+    fn round_robin_deals_wallets_in_turn() {
+        let providers = mocks(3);
+        let pool = pool(
+            AssignmentStrategy::RoundRobin,
+            &[
+                ("a", 1, &providers[0]),
+                ("b", 1, &providers[1]),
+                ("c", 1, &providers[2]),
+            ],
+        );
+        let names: Vec<String> = wallets(4)
+            .map(|address| pool.provider_for(address).name().to_string())
+            .collect();
+        assert_eq!(names, ["a", "b", "c", "a"]);
+
+        // Asking again keeps the dealt endpoint
+        assert_eq!(pool.provider_for(Address::repeat_byte(2)).name(), "b");
+    }
+
+    #[test]
+    fn weights_shape_the_shares() {
+        let providers = mocks(2);
+        let pool = pool(
+            AssignmentStrategy::Weighted,
+            &[("heavy", 9, &providers[0]), ("light", 1, &providers[1])],
+        );
+        for address in wallets(200) {
+            let _ = pool.provider_for(address);
+        }
+        let stats = pool.endpoint_stats();
+        assert!(stats[0].wallets > 150, "{stats:?}");
+        assert!(stats[1].wallets > 0, "{stats:?}");
+    }
+
+    #[test]
+    fn rejects_invalid_endpoints() {
+        let err = ProviderPool::new(
+            AssignmentStrategy::Sticky,
+            vec![
+                ("testnet".to_string(), 1, MockProvider::with_chain_id(6343)),
+                ("anvil".to_string(), 1, MockProvider::with_chain_id(31337)),
+            ],
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("anvil"));
+
+        let err = ProviderPool::new(
+            AssignmentStrategy::Sticky,
+            vec![
+                ("a".to_string(), 1, MockProvider::new()),
+                ("a".to_string(), 1, MockProvider::new()),
+            ],
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("twice"));
+
+        let zero = ProviderPool::new(
+            AssignmentStrategy::Weighted,
+            vec![("a".to_string(), 0, MockProvider::new())],
+        );
+        assert!(matches!(zero, Err(ProviderError::InvalidConfig(_))));
+
+        let empty: Result<ProviderPool<MockProvider>> =
+            ProviderPool::new(AssignmentStrategy::Sticky, vec![]);
+        assert!(matches!(empty, Err(ProviderError::InvalidConfig(_))));
+    }
+}
@@ -0,0 +1,593 @@
+//! Transaction signing behind a trait.
+//!
+//! This module provides [`TransactionSigner`], which turns a filled-in
+//! [`TransactionRequest`] into a signed raw transaction, so callers need not
+//! know where a wallet's key lives:
+//!
+//! - [`LocalSigner`] - Signs in process with a key held in memory
+//! - [`RemoteHttpSigner`] - Asks a remote signer speaking the web3signer
+//!   `eth1` API (`eth_signTransaction`), such as web3signer in front of AWS
+//!   KMS, so the key never enters the process
+//!
+//! Requests with a `gas_price` and no `max_fee_per_gas` are signed as legacy
+//! (EIP-155) transactions, all others as EIP-1559 transactions.
+//! [`ChainProvider::send_transaction`](crate::ChainProvider::send_transaction)
+//! fills in what a request leaves out before signing and sending it.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use evm_provider::{ChainProvider, LocalSigner, TransactionRequest};
+//!
+//! let signer = LocalSigner::new(PrivateKeySigner::random());
+//! let request = TransactionRequest::new().to(recipient).value(amount);
+//! let tx_hash = provider.send_transaction(request, &signer).await?;
+//! ```
+
+use std::fmt;
+use std::time::Duration;
+
+use alloy::consensus::transaction::SignerRecoverable;
+use alloy::consensus::{SignableTransaction, TxEip1559, TxEnvelope, TxLegacy};
+use alloy::eips::{Decodable2718, Encodable2718};
+use alloy::primitives::{Address, Bytes, TxKind};
+use alloy::signers::SignerSync;
+use alloy::signers::local::PrivateKeySigner;
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::json;
+use tracing::debug;
+
+use crate::error::{ProviderError, Result};
+use crate::types::TransactionRequest;
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TRANSACTION SIGNER TRAIT
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Signs transactions for one address.
+///
+/// The request must be complete: a nonce, a gas limit and a fee, as
+/// [`ChainProvider::send_transaction`](crate::ChainProvider::send_transaction)
+/// fills them in. Its `from` and `chain_id`, if set, are ignored in favour of
+/// [`address`](Self::address) and the `chain_id` signed for.
+#[async_trait]
+pub trait TransactionSigner: Send + Sync + fmt::Debug {
+    /// Address the signer signs for.
+    fn address(&self) -> Address;
+
+    /// Sign `request` for the chain `chain_id`.
+    ///
+    /// Returns the EIP-2718 encoded signed transaction, ready for
+    /// [`send_raw_transaction`](crate::ChainProvider::send_raw_transaction).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProviderError::Signing`] if the request is incomplete or the
+    /// signer refuses it, and a network error if a remote signer cannot be
+    /// reached.
+    async fn sign_transaction(&self, request: &TransactionRequest, chain_id: u64) -> Result<Bytes>;
+}
+
+/// A transaction ready to be signed.
+#[derive(Debug)]
+enum Unsigned {
+    Legacy(TxLegacy),
+    Eip1559(TxEip1559),
+}
+
+impl Unsigned {
+    /// The transaction `request` describes, on `chain_id`.
+    ///
+    /// A missing priority fee is taken to be the max fee.
+    fn from_request(request: &TransactionRequest, chain_id: u64) -> Result<Self> {
+        let missing =
+            |field: &str| ProviderError::Signing(format!("transaction request has no {field}"));
+        let nonce = request.nonce.ok_or_else(|| missing("nonce"))?;
+        let gas_limit = request.gas_limit.ok_or_else(|| missing("gas limit"))?;
+        let to = request.to.map_or(TxKind::Create, TxKind::Call);
+        let value = request.value.unwrap_or_default();
+        let input = request.data.clone().unwrap_or_default();
+
+        match (request.max_fee_per_gas, request.gas_price) {
+            (None, Some(gas_price)) => Ok(Self::Legacy(TxLegacy {
+                chain_id: Some(chain_id),
+                nonce,
+                gas_price,
+                gas_limit,
+                to,
+                value,
+                input,
+            })),
+            (Some(max_fee_per_gas), _) => Ok(Self::Eip1559(TxEip1559 {
+                chain_id,
+                nonce,
+                gas_limit,
+                max_fee_per_gas,
+                max_priority_fee_per_gas: request
+                    .max_priority_fee_per_gas
+                    .unwrap_or(max_fee_per_gas),
+                to,
+                value,
+                input,
+                ..TxEip1559::default()
+            })),
+            (None, None) => Err(missing("fee")),
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// LOCAL SIGNER
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Signs with a private key held in memory.
+///
+/// `Debug` only shows the address.
+pub struct LocalSigner {
+    /// The key.
+    signer: PrivateKeySigner,
+}
+
+impl LocalSigner {
+    /// Sign with the key of `signer`.
+    #[must_use]
+    pub const fn new(signer: PrivateKeySigner) -> Self {
+        Self { signer }
+    }
+}
+
+impl fmt::Debug for LocalSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LocalSigner")
+            .field("address", &self.signer.address())
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl TransactionSigner for LocalSigner {
+    fn address(&self) -> Address {
+        self.signer.address()
+    }
+
+    async fn sign_transaction(&self, request: &TransactionRequest, chain_id: u64) -> Result<Bytes> {
+        let signing = |e: alloy::signers::Error| ProviderError::Signing(e.to_string());
+        let envelope = match Unsigned::from_request(request, chain_id)? {
+            Unsigned::Legacy(tx) => {
+                let signature = self
+                    .signer
+                    .sign_hash_sync(&tx.signature_hash())
+                    .map_err(signing)?;
+                TxEnvelope::from(tx.into_signed(signature))
+            }
+            Unsigned::Eip1559(tx) => {
+                let signature = self
+                    .signer
+                    .sign_hash_sync(&tx.signature_hash())
+                    .map_err(signing)?;
+                TxEnvelope::from(tx.into_signed(signature))
+            }
+        };
+        Ok(envelope.encoded_2718().into())
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// REMOTE HTTP SIGNER
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Default time a remote signer has to answer.
+pub const DEFAULT_REMOTE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Signs through a remote signer's `eth_signTransaction` JSON-RPC method,
+/// as web3signer serves it in `eth1` mode.
+///
+/// The signer must hold the key of [`address`](TransactionSigner::address).
+/// Every signed transaction is checked to be signed by it before it is
+/// returned. `Debug` shows the signer's origin, not its full URL, which may
+/// hold credentials.
+pub struct RemoteHttpSigner {
+    /// HTTP client.
+    client: reqwest::Client,
+
+    /// JSON-RPC endpoint of the signer.
+    url: reqwest::Url,
+
+    /// Address signed for.
+    address: Address,
+
+    /// Time the signer has to answer.
+    timeout: Duration,
+}
+
+impl RemoteHttpSigner {
+    /// Sign for `address` through the signer at `url`, waiting at most
+    /// `timeout` for each signature.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProviderError::InvalidConfig`] if the URL is invalid, and
+    /// [`ProviderError::Connection`] if the HTTP client cannot be built.
+    pub fn new(url: &str, address: Address, timeout: Duration) -> Result<Self> {
+        let url: reqwest::Url = url
+            .parse()
+            .map_err(|e| ProviderError::InvalidConfig(format!("invalid signer URL: {e}")))?;
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .map_err(|e| ProviderError::Connection(format!("failed to build HTTP client: {e}")))?;
+        Ok(Self {
+            client,
+            url,
+            address,
+            timeout,
+        })
+    }
+
+    /// The `eth_signTransaction` request for `tx`.
+    fn request_body(&self, tx: &Unsigned) -> serde_json::Value {
+        let quantity = |n: u128| format!("{n:#x}");
+        let mut params = match tx {
+            Unsigned::Legacy(tx) => json!({
+                "nonce": quantity(tx.nonce.into()),
+                "gas": quantity(tx.gas_limit.into()),
+                "gasPrice": quantity(tx.gas_price),
+                "value": tx.value,
+                "data": tx.input,
+                "chainId": tx.chain_id.map(|id| quantity(id.into())),
+            }),
+            Unsigned::Eip1559(tx) => json!({
+                "nonce": quantity(tx.nonce.into()),
+                "gas": quantity(tx.gas_limit.into()),
+                "maxFeePerGas": quantity(tx.max_fee_per_gas),
+                "maxPriorityFeePerGas": quantity(tx.max_priority_fee_per_gas),
+                "value": tx.value,
+                "data": tx.input,
+                "chainId": quantity(tx.chain_id.into()),
+            }),
+        };
+        params["from"] = json!(self.address);
+        let to = match tx {
+            Unsigned::Legacy(tx) => tx.to,
+            Unsigned::Eip1559(tx) => tx.to,
+        };
+        if let TxKind::Call(to) = to {
+            params["to"] = json!(to);
+        }
+        json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "eth_signTransaction",
+            "params": [params],
+        })
+    }
+
+    /// Map a failed request to the signer.
+    fn request_error(&self, err: &reqwest::Error) -> ProviderError {
+        if err.is_timeout() {
+            ProviderError::Timeout(self.timeout)
+        } else if err.is_connect() {
+            ProviderError::Connection(format!("remote signer unreachable: {err}"))
+        } else {
+            ProviderError::Other(format!("remote signer request failed: {err}"))
+        }
+    }
+}
+
+impl fmt::Debug for RemoteHttpSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RemoteHttpSigner")
+            .field("address", &self.address)
+            .field("origin", &self.url.origin().ascii_serialization())
+            .field("timeout", &self.timeout)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A JSON-RPC response of a remote signer.
+#[derive(Debug, Deserialize)]
+struct SignResponse {
+    /// The signed transaction.
+    result: Option<Bytes>,
+
+    /// Why the signer refused.
+    error: Option<SignResponseError>,
+}
+
+/// The error of a JSON-RPC response.
+#[derive(Debug, Deserialize)]
+struct SignResponseError {
+    code: i64,
+    message: String,
+}
+
+#[async_trait]
+impl TransactionSigner for RemoteHttpSigner {
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    async fn sign_transaction(&self, request: &TransactionRequest, chain_id: u64) -> Result<Bytes> {
+        let tx = Unsigned::from_request(request, chain_id)?;
+        let response = self
+            .client
+            .post(self.url.clone())
+            .json(&self.request_body(&tx))
+            .send()
+            .await
+            .map_err(|e| self.request_error(&e))?;
+
+        let status = response.status();
+        let body = response.bytes().await.map_err(|e| self.request_error(&e))?;
+        let response: SignResponse = match serde_json::from_slice(&body) {
+            Ok(response) => response,
+            Err(_) if status == reqwest::StatusCode::TOO_MANY_REQUESTS => {
+                return Err(ProviderError::RateLimited(format!(
+                    "remote signer: HTTP {status}"
+                )));
+            }
+            // Not a JSON-RPC response: report the HTTP status instead
+            Err(_) if !status.is_success() => {
+                return Err(ProviderError::Signing(format!(
+                    "remote signer: HTTP {status}"
+                )));
+            }
+            Err(e) => {
+                return Err(ProviderError::InvalidResponse(format!(
+                    "remote signer: {e}"
+                )));
+            }
+        };
+        if let Some(error) = response.error {
+            return Err(ProviderError::rpc(error.code, error.message));
+        }
+        let raw = response.result.ok_or_else(|| {
+            ProviderError::InvalidResponse("remote signer answered without a result".into())
+        })?;
+
+        let signed = TxEnvelope::decode_2718(&mut raw.as_ref()).map_err(|e| {
+            ProviderError::InvalidResponse(format!("remote signer returned no transaction: {e}"))
+        })?;
+        let recovered = signed.recover_signer().map_err(|e| {
+            ProviderError::InvalidResponse(format!("remote signature does not recover: {e}"))
+        })?;
+        if recovered != self.address {
+            return Err(ProviderError::Signing(format!(
+                "remote signer signed for {recovered}, not {}",
+                self.address
+            )));
+        }
+        debug!(address = %self.address, tx_hash = %signed.tx_hash(), "Remotely signed");
+        Ok(raw)
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use alloy::primitives::U256;
+    use wiremock::matchers::{body_partial_json, method};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use super::*;
+    use crate::mock::MockProvider;
+    use crate::traits::ChainProvider;
+
+    /// The EIP-155 example: key `0x4646…46` signing a legacy transfer on
+    /// mainnet.
+    const LEGACY_KEY: &str = "4646464646464646464646464646464646464646464646464646464646464646";
+    const LEGACY_SIGNED: &str = "f86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83";
+
+    /// The first development account (Anvil, Hardhat) signing an EIP-1559
+    /// call on chain 31337, as alloy and ethers encode it.
+    const DEV_KEY: &str = "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+    const EIP1559_SIGNED: &str = "02f877827a6907843b9aca00847735940082c3509470997970c51812dc3a010c7d01b50e0d17dc79c887038d7ea4c6800084a9059cbbc001a009712ceafed7dc9a03253d825b70b0b49017c62d52df887f576a25c3afe16a75a0188a7177bbb4140927579674fa4118afe5c89474ed8f3dd8e01df7bf9c1edd89";
+
+    fn local(key: &str) -> LocalSigner {
+        LocalSigner::new(key.parse().unwrap())
+    }
+
+    fn legacy_request() -> TransactionRequest {
+        TransactionRequest::new()
+            .to(Address::repeat_byte(0x35))
+            .value(U256::from(1_000_000_000_000_000_000_u64))
+            .nonce(9)
+            .gas_limit(21_000)
+            .gas_price(20_000_000_000)
+    }
+
+    fn eip1559_request() -> TransactionRequest {
+        TransactionRequest::new()
+            .to("0x70997970C51812dc3A010C7d01b50e0d17dc79C8"
+                .parse()
+                .unwrap())
+            .value(U256::from(1_000_000_000_000_000_u64))
+            .data(Bytes::from_static(&[0xa9, 0x05, 0x9c, 0xbb]))
+            .nonce(7)
+            .gas_limit(50_000)
+            .max_fee_per_gas(2_000_000_000)
+            .max_priority_fee_per_gas(1_000_000_000)
+    }
+
+    #[tokio::test]
+    async fn local_signatures_match_known_transactions() {
+        let signer = local(LEGACY_KEY);
+        assert_eq!(
+            signer.address(),
+            "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F"
+                .parse::<Address>()
+                .unwrap()
+        );
+        let raw = signer.sign_transaction(&legacy_request(), 1).await.unwrap();
+        assert_eq!(alloy::hex::encode(&raw), LEGACY_SIGNED);
+
+        let raw = local(DEV_KEY)
+            .sign_transaction(&eip1559_request(), 31337)
+            .await
+            .unwrap();
+        assert_eq!(alloy::hex::encode(&raw), EIP1559_SIGNED);
+    }
+
+    #[tokio::test]
+    async fn incomplete_requests_are_not_signed() {
+        let signer = local(DEV_KEY);
+        for request in [
+            TransactionRequest::new().gas_limit(21_000).gas_price(1),
+            TransactionRequest::new().nonce(0).gas_price(1),
+            TransactionRequest::new().nonce(0).gas_limit(21_000),
+        ] {
+            let error = signer.sign_transaction(&request, 1).await.unwrap_err();
+            assert!(matches!(error, ProviderError::Signing(_)), "{error}");
+        }
+        assert!(!format!("{signer:?}").contains(DEV_KEY));
+    }
+
+    #[tokio::test]
+    async fn remote_signer_returns_its_signature() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({
+                "method": "eth_signTransaction",
+                "params": [{
+                    "nonce": "0x7",
+                    "gas": "0xc350",
+                    "maxFeePerGas": "0x77359400",
+                    "maxPriorityFeePerGas": "0x3b9aca00",
+                    "data": "0xa9059cbb",
+                    "chainId": "0x7a69",
+                }],
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "result": format!("0x{EIP1559_SIGNED}"),
+            })))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let address = local(DEV_KEY).address();
+        let signer = RemoteHttpSigner::new(&server.uri(), address, DEFAULT_REMOTE_TIMEOUT).unwrap();
+        let raw = signer
+            .sign_transaction(&eip1559_request(), 31337)
+            .await
+            .unwrap();
+        assert_eq!(alloy::hex::encode(&raw), EIP1559_SIGNED);
+        let requests = server.received_requests().await.unwrap();
+        let body: serde_json::Value = requests[0].body_json().unwrap();
+        let from: Address = serde_json::from_value(body["params"][0]["from"].clone()).unwrap();
+        let to: Address = serde_json::from_value(body["params"][0]["to"].clone()).unwrap();
+        assert_eq!((from, Some(to)), (address, eip1559_request().to));
+
+        // A signature for another address is not passed on
+        let other = RemoteHttpSigner::new(&server.uri(), Address::ZERO, DEFAULT_REMOTE_TIMEOUT);
+        let error = other
+            .unwrap()
+            .sign_transaction(&eip1559_request(), 31337)
+            .await;
+        assert!(matches!(error, Err(ProviderError::Signing(_))), "{error:?}");
+    }
+
+    #[tokio::test]
+    async fn remote_signer_errors_are_mapped() {
+        let server = MockServer::start().await;
+        Mock::given(body_partial_json(json!({ "params": [{ "nonce": "0x1" }] })))
+            .respond_with(ResponseTemplate::new(400).set_body_json(json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "error": { "code": -32000, "message": "Signer not found for identifier" },
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(body_partial_json(json!({ "params": [{ "nonce": "0x2" }] })))
+            .respond_with(ResponseTemplate::new(503).set_body_string("unavailable"))
+            .mount(&server)
+            .await;
+        Mock::given(body_partial_json(json!({ "params": [{ "nonce": "0x3" }] })))
+            .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_secs(5)))
+            .mount(&server)
+            .await;
+
+        let timeout = Duration::from_millis(200);
+        let signer = RemoteHttpSigner::new(&server.uri(), Address::ZERO, timeout).unwrap();
+        let signer = &signer;
+        let sign = |nonce| async move {
+            signer
+                .sign_transaction(&eip1559_request().nonce(nonce), 31337)
+                .await
+        };
+
+        let refused = sign(1).await.unwrap_err();
+        assert!(
+            matches!(&refused, ProviderError::Rpc { code: -32000, message }
+                if message.contains("Signer not found")),
+            "{refused}"
+        );
+        assert!(matches!(sign(2).await, Err(ProviderError::Signing(_))));
+        assert!(matches!(sign(3).await, Err(ProviderError::Timeout(t)) if t == timeout));
+
+        let unreachable = RemoteHttpSigner::new("http://127.0.0.1:1", Address::ZERO, timeout);
+        let error = unreachable
+            .unwrap()
+            .sign_transaction(&eip1559_request(), 31337)
+            .await;
+        assert!(error.unwrap_err().is_retryable());
+        assert!(RemoteHttpSigner::new("not a url", Address::ZERO, timeout).is_err());
+    }
+
+    /// Records the requests it signs.
+    #[derive(Debug, Default)]
+    struct Recording(Mutex<Vec<(TransactionRequest, u64)>>);
+
+    #[async_trait]
+    impl TransactionSigner for Recording {
+        fn address(&self) -> Address {
+            Address::repeat_byte(0x11)
+        }
+
+        async fn sign_transaction(
+            &self,
+            request: &TransactionRequest,
+            chain_id: u64,
+        ) -> Result<Bytes> {
+            self.0.lock().unwrap().push((request.clone(), chain_id));
+            Ok(Bytes::from_static(&[0x02]))
+        }
+    }
+
+    #[tokio::test]
+    async fn send_transaction_fills_in_the_request() {
+        let chain = MockProvider::new();
+        chain.set_nonce(Address::repeat_byte(0x11), 4);
+        chain.set_gas_price(1_000);
+        let signer = Recording::default();
+
+        let request = TransactionRequest::new().to(Address::repeat_byte(0x22));
+        chain.send_transaction(request, &signer).await.unwrap();
+        let legacy = TransactionRequest::new()
+            .nonce(9)
+            .gas_limit(21_000)
+            .gas_price(5);
+        chain.send_transaction(legacy, &signer).await.unwrap();
+
+        let requests = signer.0.lock().unwrap().clone();
+        let (filled, chain_id) = &requests[0];
+        assert_eq!(*chain_id, 31337);
+        assert_eq!(filled.from, Some(Address::repeat_byte(0x11)));
+        assert_eq!(filled.nonce, Some(4));
+        assert_eq!(filled.gas_limit, Some(100_000));
+        assert_eq!(
+            (filled.max_fee_per_gas, filled.max_priority_fee_per_gas),
+            (Some(1_000), Some(1_000))
+        );
+        let (kept, _) = &requests[1];
+        assert_eq!((kept.nonce, kept.gas_limit), (Some(9), Some(21_000)));
+        assert_eq!((kept.gas_price, kept.max_fee_per_gas), (Some(5), None));
+    }
+}
@@ -210,6 +210,11 @@ impl ChainProvider for StandardEvmProvider {
         self.chain_id
     }
 
+    #[instrument(skip(self), fields(chain_id = self.chain_id))]
+    async fn get_chain_id(&self) -> Result<u64> {
+        self.provider.get_chain_id().await.map_err(ProviderError::from)
+    }
+
     #[instrument(skip(self), fields(chain_id = self.chain_id))]
     async fn get_balance(&self, address: Address) -> Result<U256> {
         self.provider
@@ -317,6 +322,12 @@ impl ChainProvider for StandardEvmProvider {
             .map_err(ProviderError::from)
     }
 
+    /// Multicall3 lives at its canonical address on virtually every EVM chain;
+    /// callers fall back to single reads if the batched call fails.
+    fn supports_multicall(&self) -> bool {
+        true
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
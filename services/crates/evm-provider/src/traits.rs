@@ -39,6 +39,7 @@ use alloy::primitives::{Address, Bytes, TxHash, U256};
 use async_trait::async_trait;
 
 use crate::error::{ProviderError, Result};
+use crate::signer::TransactionSigner;
 use crate::types::{LogFilter, LogsPage, TransactionReceipt, TransactionRequest};
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -67,6 +68,7 @@ use crate::types::{LogFilter, LogsPage, TransactionReceipt, TransactionRequest};
 /// - [`estimate_gas`](Self::estimate_gas) - Gas estimation (default: 500,000)
 /// - [`get_pending_nonce`](Self::get_pending_nonce) - Includes mempool (default: same as get_nonce)
 /// - [`get_token_balance`](Self::get_token_balance) - ERC20 balance (default: uses call)
+/// - [`send_transaction`](Self::send_transaction) - Fill in, sign and submit a request
 #[async_trait]
 pub trait ChainProvider: Send + Sync + std::fmt::Debug + 'static {
     /// Chain identifier (e.g., 1 for Ethereum mainnet, 6343 for MegaETH testnet).
@@ -85,6 +87,16 @@ pub trait ChainProvider: Send + Sync + std::fmt::Debug + 'static {
     /// ```
     fn as_any(&self) -> &dyn std::any::Any;
 
+    /// Query the chain ID from the endpoint.
+    ///
+    /// Unlike [`chain_id`](Self::chain_id), which is read once on connection,
+    /// this makes a request, so it doubles as a cheap liveness probe.
+    ///
+    /// Default implementation returns the cached [`chain_id`](Self::chain_id).
+    async fn get_chain_id(&self) -> Result<u64> {
+        Ok(self.chain_id())
+    }
+
     /// Get native token balance (ETH) for an address.
     ///
     /// # Arguments
@@ -130,6 +142,37 @@ pub trait ChainProvider: Send + Sync + std::fmt::Debug + 'static {
     /// use [`wait_for_receipt`](Self::wait_for_receipt) to wait for confirmation.
     async fn send_raw_transaction(&self, tx: Bytes) -> Result<TxHash>;
 
+    /// Sign a transaction request with `signer` and send it.
+    ///
+    /// Fills in what the request leaves out first: the signer's pending
+    /// nonce, the current [gas price](Self::gas_price) as both max fee and
+    /// priority fee, and an [estimated](Self::estimate_gas) gas limit. A
+    /// request with a `gas_price` of its own is sent as a legacy transaction.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if filling in the request, signing it or sending it
+    /// fails.
+    async fn send_transaction(
+        &self,
+        request: TransactionRequest,
+        signer: &dyn TransactionSigner,
+    ) -> Result<TxHash> {
+        let mut request = request.from(signer.address());
+        if request.nonce.is_none() {
+            request.nonce = Some(self.get_pending_nonce(signer.address()).await?);
+        }
+        if request.gas_price.is_none() && request.max_fee_per_gas.is_none() {
+            let fee = self.gas_price().await?;
+            request = request.max_fee_per_gas(fee).max_priority_fee_per_gas(fee);
+        }
+        if request.gas_limit.is_none() {
+            request.gas_limit = Some(self.estimate_gas(&request).await?);
+        }
+        let raw = signer.sign_transaction(&request, self.chain_id()).await?;
+        self.send_raw_transaction(raw).await
+    }
+
     /// Wait for a transaction to be confirmed.
     ///
     /// # Arguments
@@ -154,7 +197,13 @@ pub trait ChainProvider: Send + Sync + std::fmt::Debug + 'static {
     /// # Note
     ///
     /// MegaETH gas estimation is unreliable - the `MegaEthProvider` overrides
-    /// this to use a fixed gas limit.
+    /// this to use a fixed gas limit, failing only if estimation reverts.
+    ///
+    /// # Errors
+    ///
+    /// Providers that estimate return an error if the transaction would
+    /// revert; its [`revert_reason`](crate::ProviderError::revert_reason)
+    /// tells why.
     async fn estimate_gas(&self, _tx: &TransactionRequest) -> Result<u64> {
         tracing::debug!(
             gas = 500_000,
@@ -230,6 +279,42 @@ pub trait ChainProvider: Send + Sync + std::fmt::Debug + 'static {
 
         Ok(U256::from_be_slice(&result[..32]))
     }
+
+    /// Whether [`get_token_balances`](Self::get_token_balances) can batch reads
+    /// through [Multicall3](crate::multicall).
+    ///
+    /// Default: `false`. Override on chains where Multicall3 is deployed at
+    /// its canonical address.
+    fn supports_multicall(&self) -> bool {
+        false
+    }
+
+    /// Get ERC20 token balances for several `(token, account)` pairs.
+    ///
+    /// Returns one entry per query, in order; `None` marks a read that failed
+    /// without failing the batch. When [`supports_multicall`](Self::supports_multicall)
+    /// is `true` the default implementation issues a single Multicall3
+    /// `aggregate3` call, otherwise it falls back to one
+    /// [`get_token_balance`](Self::get_token_balance) per query.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the batched call itself fails, e.g. because
+    /// Multicall3 is not deployed on this chain.
+    async fn get_token_balances(
+        &self,
+        queries: &[(Address, Address)],
+    ) -> Result<Vec<Option<U256>>> {
+        if self.supports_multicall() {
+            return crate::multicall::token_balances(self, queries).await;
+        }
+
+        let mut balances = Vec::with_capacity(queries.len());
+        for &(token, account) in queries {
+            balances.push(self.get_token_balance(token, account).await.ok());
+        }
+        Ok(balances)
+    }
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -506,6 +591,10 @@ impl<T: ChainProvider + ?Sized> ChainProvider for std::sync::Arc<T> {
         self
     }
 
+    async fn get_chain_id(&self) -> Result<u64> {
+        (**self).get_chain_id().await
+    }
+
     async fn get_balance(&self, address: Address) -> Result<U256> {
         (**self).get_balance(address).await
     }
@@ -522,6 +611,14 @@ impl<T: ChainProvider + ?Sized> ChainProvider for std::sync::Arc<T> {
         (**self).send_raw_transaction(tx).await
     }
 
+    async fn send_transaction(
+        &self,
+        request: TransactionRequest,
+        signer: &dyn TransactionSigner,
+    ) -> Result<TxHash> {
+        (**self).send_transaction(request, signer).await
+    }
+
     async fn wait_for_receipt(
         &self,
         tx_hash: TxHash,
@@ -549,6 +646,17 @@ impl<T: ChainProvider + ?Sized> ChainProvider for std::sync::Arc<T> {
     async fn get_token_balance(&self, token: Address, account: Address) -> Result<U256> {
         (**self).get_token_balance(token, account).await
     }
+
+    fn supports_multicall(&self) -> bool {
+        (**self).supports_multicall()
+    }
+
+    async fn get_token_balances(
+        &self,
+        queries: &[(Address, Address)],
+    ) -> Result<Vec<Option<U256>>> {
+        (**self).get_token_balances(queries).await
+    }
 }
 
 #[async_trait]
@@ -833,4 +941,19 @@ mod tests {
         assert!(msg.contains("16 bytes"), "error: {msg}");
         assert!(msg.contains("expected 32"), "error: {msg}");
     }
+
+    #[tokio::test]
+    async fn get_token_balances_falls_back_to_single_reads() {
+        let provider = CallCapturingProvider::valid_response(U256::from(1000));
+        let token = Address::repeat_byte(0xaa);
+        let queries = [(token, Address::repeat_byte(0xbb)), (token, Address::ZERO)];
+
+        let balances = provider.get_token_balances(&queries).await.unwrap();
+        assert_eq!(balances, [Some(U256::from(1000)); 2]);
+
+        // A failed read is reported per query, not for the whole batch
+        let provider = CallCapturingProvider::empty_response();
+        let balances = provider.get_token_balances(&queries).await.unwrap();
+        assert_eq!(balances, [None, None]);
+    }
 }
@@ -136,6 +136,20 @@ impl TransactionRequest {
         self
     }
 
+    /// Set the max fee per gas (EIP-1559 transactions).
+    #[must_use]
+    pub const fn max_fee_per_gas(mut self, max_fee_per_gas: u128) -> Self {
+        self.max_fee_per_gas = Some(max_fee_per_gas);
+        self
+    }
+
+    /// Set the max priority fee per gas (EIP-1559 transactions).
+    #[must_use]
+    pub const fn max_priority_fee_per_gas(mut self, max_priority_fee_per_gas: u128) -> Self {
+        self.max_priority_fee_per_gas = Some(max_priority_fee_per_gas);
+        self
+    }
+
     /// Set the nonce.
     #[must_use]
     pub const fn nonce(mut self, nonce: u64) -> Self {
@@ -0,0 +1,124 @@
+//! Time source for scheduling and safety decisions.
+//!
+//! Everything in fleet-core that compares against "now" (scheduling, circuit
+//! breaker cooldowns, balance freshness) reads the time from a [`Clock`].
+//! Live services use [`SystemClock`]; simulations and tests use a
+//! [`VirtualClock`] that only moves when told to, so a month of fleet activity
+//! can be replayed in seconds and reproduced exactly.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicI64, Ordering};
+
+use chrono::{DateTime, Duration, Utc};
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// CLOCK
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Source of the current time.
+pub trait Clock: Send + Sync + std::fmt::Debug {
+    /// Get the current UTC time.
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// Shared handle to a clock.
+pub type SharedClock = Arc<dyn Clock>;
+
+/// Handle to the system clock.
+#[must_use]
+pub fn system_clock() -> SharedClock {
+    Arc::new(SystemClock)
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// SYSTEM CLOCK
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Clock returning the real system time.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// VIRTUAL CLOCK
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Clock that only moves when advanced, with millisecond precision.
+///
+/// # Example
+///
+/// ```
+/// use chrono::{Duration, TimeZone, Utc};
+/// use fleet_core::clock::{Clock, VirtualClock};
+///
+/// let start = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+/// let clock = VirtualClock::new(start);
+///
+/// clock.advance(Duration::days(30));
+/// assert_eq!(clock.now(), start + Duration::days(30));
+/// ```
+#[derive(Debug)]
+pub struct VirtualClock {
+    /// Current time as Unix milliseconds.
+    millis: AtomicI64,
+}
+
+impl VirtualClock {
+    /// Create a virtual clock starting at `start`.
+    #[must_use]
+    pub const fn new(start: DateTime<Utc>) -> Self {
+        Self {
+            millis: AtomicI64::new(start.timestamp_millis()),
+        }
+    }
+
+    /// Move the clock forward by `duration`.
+    pub fn advance(&self, duration: Duration) {
+        self.millis
+            .fetch_add(duration.num_milliseconds(), Ordering::SeqCst);
+    }
+
+    /// Set the clock to `time`.
+    ///
+    /// Unlike [`advance`](Self::advance) this may move the clock backwards.
+    pub fn set(&self, time: DateTime<Utc>) {
+        self.millis.store(time.timestamp_millis(), Ordering::SeqCst);
+    }
+}
+
+impl Clock for VirtualClock {
+    fn now(&self) -> DateTime<Utc> {
+        DateTime::from_timestamp_millis(self.millis.load(Ordering::SeqCst)).unwrap_or_default()
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn virtual_clock_moves_only_when_told() {
+        let start = Utc::now();
+        let clock = VirtualClock::new(start);
+
+        assert_eq!(clock.now().timestamp_millis(), start.timestamp_millis());
+
+        clock.advance(Duration::milliseconds(1500));
+        assert_eq!(
+            clock.now().timestamp_millis(),
+            start.timestamp_millis() + 1500
+        );
+
+        clock.set(start - Duration::hours(1));
+        assert!(clock.now() < start);
+    }
+}
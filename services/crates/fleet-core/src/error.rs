@@ -1,13 +1,100 @@
 //! Error types for fleet-core operations.
 //!
 //! This module defines the error types used throughout the fleet-core crate.
-//! Errors are categorized by their source (wallet, plugin, scheduler, etc.).
+//! Errors are categorized by their source (wallet, plugin, scheduler, etc.)
+//! and, through [`ErrorClass`], by how the engine should react to them.
 
+use std::time::Duration;
+
+use evm_provider::ProviderError;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 /// Result type alias for fleet-core operations.
 pub type Result<T> = std::result::Result<T, FleetError>;
 
+// ═══════════════════════════════════════════════════════════════════════════════
+// ERROR CLASS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// How the engine should react to an error.
+///
+/// | Class | Engine reaction |
+/// |-------|-----------------|
+/// | `Transient` | Retry in place; counts toward the circuit breaker once retries run out |
+/// | `RateLimited` | Push the wallet's next action out and feed the global breaker |
+/// | `Permanent` | Give up on the action; counts toward the circuit breaker |
+/// | `Configuration` | Give up on the action; needs an operator, not a breaker |
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorClass {
+    /// A passing condition such as a network blip or RPC timeout.
+    Transient,
+
+    /// The endpoint is throttling requests.
+    RateLimited,
+
+    /// Retrying will not help (e.g. a decoded contract revert).
+    Permanent,
+
+    /// The fleet is misconfigured.
+    Configuration,
+}
+
+impl ErrorClass {
+    /// All classes.
+    pub const ALL: [Self; 4] = [
+        Self::Transient,
+        Self::RateLimited,
+        Self::Permanent,
+        Self::Configuration,
+    ];
+
+    /// Check whether the failed operation may succeed if tried again later.
+    #[must_use]
+    pub const fn is_retryable(self) -> bool {
+        matches!(self, Self::Transient | Self::RateLimited)
+    }
+
+    /// Name of the class as used in serialized form.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Transient => "transient",
+            Self::RateLimited => "rate_limited",
+            Self::Permanent => "permanent",
+            Self::Configuration => "configuration",
+        }
+    }
+}
+
+impl std::fmt::Display for ErrorClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<&ProviderError> for ErrorClass {
+    fn from(error: &ProviderError) -> Self {
+        if error.is_rate_limited() {
+            Self::RateLimited
+        } else if error.is_retryable() {
+            Self::Transient
+        } else if matches!(
+            error,
+            ProviderError::InvalidConfig(_) | ProviderError::Unsupported(_)
+        ) {
+            Self::Configuration
+        } else {
+            Self::Permanent
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// FLEET ERROR
+// ═══════════════════════════════════════════════════════════════════════════════
+
 /// Errors that can occur in fleet-core operations.
 #[derive(Debug, Error)]
 pub enum FleetError {
@@ -41,9 +128,39 @@ pub enum FleetError {
     #[error("invalid plugin data: {0}")]
     InvalidPluginData(String),
 
+    /// Plugin state on a wallet does not match the plugin's schema.
+    ///
+    /// The stored state is left as is, so nothing is lost if a newer plugin
+    /// version wrote it.
+    #[error("plugin state of {plugin} is not a valid {type_name}: {message}")]
+    PluginState {
+        /// Plugin the state belongs to.
+        plugin: String,
+        /// Type the state was read as.
+        type_name: &'static str,
+        /// What went wrong.
+        message: String,
+    },
+
     /// Plugin execution failed.
-    #[error("plugin execution failed: {0}")]
-    PluginExecution(String),
+    #[error("plugin execution failed: {message}")]
+    PluginExecution {
+        /// How the engine should react.
+        class: ErrorClass,
+        /// What went wrong.
+        message: String,
+    },
+
+    /// Plugin call ran past its timeout and was cancelled.
+    #[error("plugin {plugin} did not {call} within {after:?}")]
+    PluginTimeout {
+        /// Plugin that was called.
+        plugin: String,
+        /// What it was asked to do.
+        call: &'static str,
+        /// How long it was given.
+        after: Duration,
+    },
 
     // ─────────────────────────────────────────────────────────────────────────
     // Safety errors
@@ -61,12 +178,17 @@ pub enum FleetError {
     #[error("global pause is active")]
     GlobalPause,
 
+    /// This node no longer holds the fleet's lease, so another node may be
+    /// acting for the fleet.
+    #[error("fleet lease lost")]
+    LeaseLost,
+
     // ─────────────────────────────────────────────────────────────────────────
     // Provider errors
     // ─────────────────────────────────────────────────────────────────────────
     /// Chain provider error (wrapped from evm-provider).
     #[error("provider error: {0}")]
-    Provider(#[from] evm_provider::ProviderError),
+    Provider(#[from] ProviderError),
 
     // ─────────────────────────────────────────────────────────────────────────
     // Configuration errors
@@ -81,39 +203,72 @@ pub enum FleetError {
 }
 
 impl FleetError {
+    /// Create a plugin execution error of the given class.
+    #[must_use]
+    pub fn plugin(class: ErrorClass, message: impl Into<String>) -> Self {
+        Self::PluginExecution {
+            class,
+            message: message.into(),
+        }
+    }
+
+    /// Create an error for a plugin `call`, e.g. `"execute"`, cancelled
+    /// after `after`.
+    #[must_use]
+    pub fn timeout(plugin: impl Into<String>, call: &'static str, after: Duration) -> Self {
+        Self::PluginTimeout {
+            plugin: plugin.into(),
+            call,
+            after,
+        }
+    }
+
+    /// How the engine should react to this error.
+    #[must_use]
+    pub fn class(&self) -> ErrorClass {
+        match self {
+            Self::PluginExecution { class, .. } => *class,
+            Self::Provider(e) => e.into(),
+            // These are all transient conditions that will resolve on their own
+            Self::CircuitBreakerTripped { .. }
+            | Self::PluginTimeout { .. }
+            | Self::WalletAfk(_)
+            | Self::WalletDisabled(_)
+            | Self::GlobalPause
+            | Self::LeaseLost => ErrorClass::Transient,
+            Self::WalletNotFound(_)
+            | Self::PluginNotFound(_)
+            | Self::UnknownAction(_)
+            | Self::PluginState { .. }
+            | Self::InvalidConfig(_) => ErrorClass::Configuration,
+            Self::InvalidPluginData(_) | Self::Serialization(_) => ErrorClass::Permanent,
+        }
+    }
+
     /// Returns true if this error indicates a transient condition that may resolve.
     ///
     /// Transient errors include network issues, rate limits, and temporary
     /// service unavailability. Non-transient errors include invalid data,
     /// missing wallets, and configuration issues.
     #[must_use]
-    #[allow(clippy::missing_const_for_fn)] // is_retryable() is not const
     pub fn is_transient(&self) -> bool {
-        match self {
-            Self::Provider(e) => e.is_retryable(),
-            // These are all transient conditions that will resolve on their own
-            Self::CircuitBreakerTripped { .. }
-            | Self::WalletAfk(_)
-            | Self::GlobalPause => true,
-            _ => false,
-        }
+        self.class().is_retryable()
     }
 
     /// Returns true if this error should trigger circuit breaker increment.
     ///
-    /// Some errors (like AFK or global pause) are expected and shouldn't
-    /// count toward circuit breaker limits.
+    /// Only [`Permanent`](ErrorClass::Permanent) and
+    /// [`Transient`](ErrorClass::Transient) errors count; the engine records
+    /// transient ones only after its in-place retries have failed. Expected
+    /// conditions (like AFK or global pause) never count.
     #[must_use]
-    pub const fn counts_toward_circuit_breaker(&self) -> bool {
+    pub fn counts_toward_circuit_breaker(&self) -> bool {
         match self {
-            // Expected/config errors - don't count toward breaker
             Self::WalletAfk(_)
             | Self::GlobalPause
             | Self::WalletDisabled(_)
-            | Self::CircuitBreakerTripped { .. }
-            | Self::PluginNotFound(_)
-            | Self::InvalidConfig(_) => false,
-            _ => true,
+            | Self::CircuitBreakerTripped { .. } => false,
+            _ => matches!(self.class(), ErrorClass::Transient | ErrorClass::Permanent),
         }
     }
 }
@@ -137,6 +292,29 @@ mod tests {
     fn circuit_breaker_counting() {
         assert!(!FleetError::GlobalPause.counts_toward_circuit_breaker());
         assert!(!FleetError::WalletDisabled("x".into()).counts_toward_circuit_breaker());
-        assert!(FleetError::PluginExecution("x".into()).counts_toward_circuit_breaker());
+        assert!(!FleetError::InvalidConfig("x".into()).counts_toward_circuit_breaker());
+        let timeout = FleetError::timeout("ghostnet", "execute", Duration::from_secs(120));
+        assert_eq!(timeout.class(), ErrorClass::Transient);
+        assert!(timeout.counts_toward_circuit_breaker());
+
+        let counted = |class| FleetError::plugin(class, "x").counts_toward_circuit_breaker();
+        assert!(counted(ErrorClass::Transient));
+        assert!(counted(ErrorClass::Permanent));
+        assert!(!counted(ErrorClass::RateLimited));
+        assert!(!counted(ErrorClass::Configuration));
+    }
+
+    #[test]
+    fn provider_errors_are_classified() {
+        let class = |e: ProviderError| FleetError::from(e).class();
+
+        assert_eq!(
+            class(ProviderError::Timeout(std::time::Duration::from_secs(5))),
+            ErrorClass::Transient
+        );
+        assert_eq!(class(ProviderError::RateLimited("HTTP 429".into())), ErrorClass::RateLimited);
+        assert_eq!(class(ProviderError::rpc(-32005, "limit")), ErrorClass::RateLimited);
+        assert_eq!(class(ProviderError::Encoding("bad abi".into())), ErrorClass::Permanent);
+        assert_eq!(class(ProviderError::InvalidConfig("no url".into())), ErrorClass::Configuration);
     }
 }
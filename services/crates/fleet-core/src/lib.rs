@@ -68,6 +68,10 @@
 //! - Auto-resets after cooldown period
 //! - Per-wallet granularity
 //!
+//! [`GlobalBreaker`](safety::GlobalBreaker) holds back the whole fleet when
+//! the RPC endpoint keeps rate limiting it. Which breaker an error feeds, if
+//! any, follows from its [`ErrorClass`](error::ErrorClass).
+//!
 //! ## Scheduling
 //!
 //! [`Scheduler`](scheduler::Scheduler) manages action timing:
@@ -75,6 +79,25 @@
 //! - Random jitter for natural variation
 //! - Active hours consideration
 //!
+//! ## Time
+//!
+//! Time-dependent components read "now" from a [`Clock`](clock::Clock).
+//! Swapping the default [`SystemClock`](clock::SystemClock) for a
+//! [`VirtualClock`](clock::VirtualClock) makes them deterministic, for tests
+//! and simulations.
+//!
+//! ## Secrets
+//!
+//! Private keys and signed transactions are held as
+//! [`Sensitive`](sensitive::Sensitive) values, which format as `[redacted]`;
+//! [`redact`](sensitive::redact) scrubs log output of those that were not.
+//!
+//! ## Validation
+//!
+//! Config checks collect what they find into a
+//! [`ConfigReport`](validation::ConfigReport) of errors and warnings, each at
+//! the dotted path of the offending key.
+//!
 //! # Example Usage
 //!
 //! ```ignore
@@ -116,7 +139,7 @@
 //!     
 //!     // Schedule next action
 //!     let profile = BehaviorProfile::whale();
-//!     let next = scheduler.calculate_next_action(&profile);
+//!     let next = scheduler.calculate_next_action(&wallet.id, &profile);
 //! }
 //! ```
 //!
@@ -130,12 +153,14 @@
 // MODULES
 // ═══════════════════════════════════════════════════════════════════════════════
 
+pub mod clock;
 pub mod error;
 pub mod metrics;
 pub mod plugins;
 pub mod profiles;
 pub mod safety;
 pub mod scheduler;
+pub mod validation;
 pub mod wallet;
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -143,19 +168,24 @@ pub mod wallet;
 // ═══════════════════════════════════════════════════════════════════════════════
 
 // Error types
-pub use error::{FleetError, Result};
+pub use error::{ErrorClass, FleetError, Result};
+
+// Clock
+pub use clock::{Clock, SharedClock, SystemClock, VirtualClock};
 
 // Wallet
 pub use wallet::WalletState;
 
 // Profiles
-pub use profiles::BehaviorProfile;
+pub use profiles::{BehaviorProfile, ProfileCatalog};
 
 // Plugins
-pub use plugins::{Action, ActionId, ActionPlugin, ActionResult, PluginContext, PluginRegistry};
+pub use plugins::{
+    Action, ActionId, ActionPlugin, ActionResult, ActionStatus, PluginContext, PluginRegistry,
+};
 
 // Safety
-pub use safety::CircuitBreaker;
+pub use safety::{BudgetManager, CircuitBreaker, GlobalBreaker};
 
 // Scheduler
 pub use scheduler::Scheduler;
@@ -163,6 +193,9 @@ pub use scheduler::Scheduler;
 // Metrics
 pub use metrics::{ActionMetrics, FleetMetrics, FleetSnapshot};
 
+// Secrets, kept in a crate of their own so the provider crates share them
+pub use sensitive::{self, Sensitive};
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // PRELUDE
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -173,9 +206,11 @@ pub use metrics::{ActionMetrics, FleetMetrics, FleetSnapshot};
 /// use fleet_core::prelude::*;
 /// ```
 pub mod prelude {
-    pub use crate::error::{FleetError, Result};
+    pub use crate::error::{ErrorClass, FleetError, Result};
     pub use crate::metrics::{ActionMetrics, FleetMetrics};
-    pub use crate::plugins::{Action, ActionId, ActionPlugin, ActionResult, PluginRegistry};
+    pub use crate::plugins::{
+        Action, ActionId, ActionPlugin, ActionResult, ActionStatus, PluginRegistry,
+    };
     pub use crate::profiles::BehaviorProfile;
     pub use crate::safety::CircuitBreaker;
     pub use crate::scheduler::Scheduler;
@@ -0,0 +1,247 @@
+//! Lockstep detection on the fleet's realized activity.
+//!
+//! Profiles can be composed to differ, yet wallets may still end up acting
+//! together, e.g. after a restart, a shared trigger or a global breaker
+//! releasing them at once. [`ActivityCorrelation`] measures how the recent
+//! actions of the fleet concentrate in time: the actions are bucketed
+//! (10 minutes by default) and the busiest bucket's share of the window is
+//! compared against a threshold.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+/// How the fleet's activity is checked for lockstep.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CorrelationSettings {
+    /// Width of the time buckets actions are counted in.
+    pub bucket: Duration,
+
+    /// How far back actions are looked at.
+    pub window: Duration,
+
+    /// Share of the window's actions (0.0-1.0) in one bucket above which the
+    /// fleet is flagged.
+    pub threshold: f64,
+
+    /// Fewest actions in the window for the share to mean anything.
+    pub min_actions: usize,
+}
+
+impl Default for CorrelationSettings {
+    fn default() -> Self {
+        Self {
+            bucket: Duration::minutes(10),
+            window: Duration::hours(6),
+            threshold: 0.4,
+            min_actions: 20,
+        }
+    }
+}
+
+/// Concentration of the fleet's recent actions in time.
+///
+/// # Example
+///
+/// ```
+/// use chrono::{Duration, TimeZone, Utc};
+/// use fleet_core::metrics::{ActivityCorrelation, CorrelationSettings};
+///
+/// let now = Utc.with_ymd_and_hms(2025, 1, 1, 12, 0, 0).unwrap();
+/// let settings = CorrelationSettings::default();
+///
+/// // 30 wallets acting within the same few minutes
+/// let lockstep = (0..30).map(|i| now - Duration::minutes(45) + Duration::seconds(i * 10));
+/// let correlation = ActivityCorrelation::measure(lockstep, now, &settings);
+/// assert!(correlation.flagged);
+///
+/// // One action every 10 minutes
+/// let spread = (0..30).map(|i| now - Duration::minutes(i * 10 + 1));
+/// assert!(!ActivityCorrelation::measure(spread, now, &settings).flagged);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct ActivityCorrelation {
+    /// Actions in the window.
+    pub actions: usize,
+
+    /// Actions in the busiest bucket of the window.
+    pub busiest_bucket_actions: usize,
+
+    /// Share of the window's actions in the busiest bucket (0.0-1.0).
+    pub concentration: f64,
+
+    /// Whether the concentration exceeds the threshold, with enough actions
+    /// to tell.
+    pub flagged: bool,
+}
+
+impl ActivityCorrelation {
+    /// Measure the concentration of actions taken at `times`, as of `now`.
+    ///
+    /// Buckets are aligned to the Unix epoch, so the same actions always
+    /// fall into the same buckets. Actions outside the window, including
+    /// any after `now`, are ignored.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)] // Action counts are small
+    pub fn measure(
+        times: impl IntoIterator<Item = DateTime<Utc>>,
+        now: DateTime<Utc>,
+        settings: &CorrelationSettings,
+    ) -> Self {
+        let bucket_millis = settings.bucket.num_milliseconds().max(1);
+        let since = now - settings.window;
+
+        let mut buckets: Vec<i64> = times
+            .into_iter()
+            .filter(|at| *at > since && *at <= now)
+            .map(|at| at.timestamp_millis().div_euclid(bucket_millis))
+            .collect();
+        buckets.sort_unstable();
+
+        let actions = buckets.len();
+        let busiest_bucket_actions = buckets
+            .chunk_by(|a, b| a == b)
+            .map(<[i64]>::len)
+            .max()
+            .unwrap_or(0);
+        let concentration = if actions == 0 {
+            0.0
+        } else {
+            busiest_bucket_actions as f64 / actions as f64
+        };
+
+        Self {
+            actions,
+            busiest_bucket_actions,
+            concentration,
+            flagged: actions >= settings.min_actions && concentration > settings.threshold,
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn noon() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 1, 1, 12, 0, 0).unwrap()
+    }
+
+    /// Action times of `wallets` wallets acting every `every` from `start`
+    /// on, each offset from the previous by `offset`.
+    fn timeline(
+        wallets: i32,
+        actions: i32,
+        start: DateTime<Utc>,
+        every: Duration,
+        offset: Duration,
+    ) -> Vec<DateTime<Utc>> {
+        (0..wallets)
+            .flat_map(|wallet| (0..actions).map(move |n| start + offset * wallet + every * n))
+            .collect()
+    }
+
+    #[test]
+    fn empty_activity_is_not_flagged() {
+        let correlation = ActivityCorrelation::measure([], noon(), &CorrelationSettings::default());
+
+        assert_eq!(correlation, ActivityCorrelation::default());
+    }
+
+    #[test]
+    fn lockstep_wallets_are_flagged() {
+        // 10 wallets acting hourly within seconds of each other: each of the
+        // 6 hours holds a sixth of the actions in one bucket
+        let start = noon() - Duration::hours(6) + Duration::minutes(1);
+        let times = timeline(10, 6, start, Duration::hours(1), Duration::seconds(5));
+        let settings = CorrelationSettings {
+            threshold: 0.15,
+            ..CorrelationSettings::default()
+        };
+
+        let correlation = ActivityCorrelation::measure(times, noon(), &settings);
+        assert_eq!(correlation.actions, 60);
+        assert_eq!(correlation.busiest_bucket_actions, 10);
+        assert!((correlation.concentration - 1.0 / 6.0).abs() < 1e-9);
+        assert!(correlation.flagged);
+    }
+
+    #[test]
+    fn burst_of_the_whole_fleet_is_flagged() {
+        // 20 wallets spread out, then 25 more actions in one 10-minute bucket
+        let start = noon() - Duration::hours(5);
+        let mut times = timeline(20, 1, start, Duration::hours(1), Duration::minutes(13));
+        times.extend(timeline(
+            25,
+            1,
+            noon() - Duration::minutes(9),
+            Duration::hours(1),
+            Duration::seconds(20),
+        ));
+
+        let correlation =
+            ActivityCorrelation::measure(times, noon(), &CorrelationSettings::default());
+        assert_eq!(correlation.actions, 45);
+        assert_eq!(correlation.busiest_bucket_actions, 25);
+        assert!(correlation.concentration > 0.4);
+        assert!(correlation.flagged);
+    }
+
+    #[test]
+    fn staggered_wallets_are_not_flagged() {
+        // 36 wallets, one per 10-minute bucket of the window
+        let start = noon() - Duration::hours(6) + Duration::minutes(1);
+        let times = timeline(36, 1, start, Duration::hours(1), Duration::minutes(10));
+
+        let correlation =
+            ActivityCorrelation::measure(times, noon(), &CorrelationSettings::default());
+        assert_eq!(correlation.actions, 36);
+        assert_eq!(correlation.busiest_bucket_actions, 1);
+        assert!(!correlation.flagged);
+    }
+
+    #[test]
+    fn too_few_actions_are_not_flagged() {
+        let times = timeline(
+            5,
+            1,
+            noon() - Duration::minutes(5),
+            Duration::hours(1),
+            Duration::seconds(1),
+        );
+
+        let correlation =
+            ActivityCorrelation::measure(times, noon(), &CorrelationSettings::default());
+        assert!((correlation.concentration - 1.0).abs() < f64::EPSILON);
+        assert!(!correlation.flagged);
+    }
+
+    #[test]
+    fn actions_outside_the_window_are_ignored() {
+        let settings = CorrelationSettings::default();
+        let mut times = timeline(
+            30,
+            1,
+            noon() - Duration::hours(7),
+            Duration::hours(1),
+            Duration::seconds(1),
+        );
+        times.extend(timeline(
+            30,
+            1,
+            noon() + Duration::minutes(1),
+            Duration::hours(1),
+            Duration::seconds(1),
+        ));
+        times.push(noon() - Duration::minutes(1));
+
+        let correlation = ActivityCorrelation::measure(times, noon(), &settings);
+        assert_eq!(correlation.actions, 1);
+        assert!(!correlation.flagged);
+    }
+}
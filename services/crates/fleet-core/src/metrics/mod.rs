@@ -16,30 +16,75 @@
 //! - **Gauges**: Track current values (active wallets, tripped breakers)
 //! - **Histograms**: Track distributions (action latency, gas usage)
 //!
+//! # Concurrency
+//!
+//! [`FleetMetrics`] is recorded into through `&self` and can be shared across
+//! tasks behind an `Arc`. Recording an action is a handful of relaxed atomic
+//! increments, one sharded map lookup per key and a ring buffer slot write;
+//! no call holds a lock across another. Percentile reads copy and sort at
+//! most 1000 samples.
+//!
+//! # Correlation
+//!
+//! The times of recent actions are kept as well, for an hour-of-day
+//! histogram of the fleet's activity and an [`ActivityCorrelation`] that
+//! flags wallets acting in lockstep (see [`correlation`]).
+//!
 //! # Example
 //!
 //! ```
 //! use fleet_core::metrics::{FleetMetrics, ActionMetrics};
+//! use fleet_core::plugins::{ActionResult, ActionStatus};
 //!
-//! let mut metrics = FleetMetrics::new();
+//! let metrics = FleetMetrics::new();
 //!
 //! // Record an action
 //! metrics.record_action(ActionMetrics {
 //!     plugin_id: "ghostnet".to_string(),
 //!     action_id: "ghostnet.jack_in".to_string(),
 //!     wallet_id: "whale_1".to_string(),
-//!     success: true,
-//!     duration_ms: 150,
+//!     status: ActionStatus::Succeeded,
+//!     duration_ms: Some(150),
 //!     gas_used: Some(250_000),
+//!     gas_estimate: Some(240_000),
+//!     gas_cost_wei: None,
+//!     replacement: None,
+//!     submission: None,
 //! });
 //!
-//! assert_eq!(metrics.total_actions(), 1);
+//! // Or straight from an action result
+//! let result = ActionResult::failure("insufficient balance");
+//! metrics.record_result("ghostnet", "ghostnet.add_stake", "whale_1", &result);
+//!
+//! assert_eq!(metrics.total_actions(), 2);
 //! assert_eq!(metrics.successful_actions(), 1);
+//! assert_eq!(metrics.actions_with_status(ActionStatus::Dropped), 1);
 //! ```
 
-use std::collections::{HashMap, VecDeque};
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use chrono::{DateTime, TimeZone, Timelike, Utc};
+use dashmap::DashMap;
+use evm_provider::EndpointStats;
+use serde::{Deserialize, Serialize};
+
+use crate::clock::{SharedClock, system_clock};
+use crate::error::ErrorClass;
+use crate::plugins::{
+    ActionResult, ActionStatus, PluginHealthStatus, ReplacementOutcome, Submission, SubmissionPath,
+};
+use crate::safety::DegradationStatus;
+use crate::scheduler::{GroupStats, RampProgress};
+
+pub mod correlation;
+pub mod pnl;
 
-use chrono::{DateTime, Utc};
+pub use correlation::{ActivityCorrelation, CorrelationSettings};
+pub use pnl::{PnlSummary, PnlTotals};
 
 // ═══════════════════════════════════════════════════════════════════════════════
 // METRICS TYPES
@@ -57,19 +102,62 @@ pub struct ActionMetrics {
     /// Wallet that performed the action.
     pub wallet_id: String,
 
-    /// Whether the action succeeded.
-    pub success: bool,
+    /// Outcome of the action.
+    pub status: ActionStatus,
 
-    /// Execution duration in milliseconds.
-    pub duration_ms: u64,
+    /// Execution duration in milliseconds, if measured.
+    pub duration_ms: Option<u64>,
 
     /// Gas used (if transaction was sent).
     pub gas_used: Option<u64>,
+
+    /// Gas the transaction was estimated to use, if estimated.
+    pub gas_estimate: Option<u64>,
+
+    /// Gas cost in wei (if gas used and price are known).
+    pub gas_cost_wei: Option<u128>,
+
+    /// How a transaction that was not mined in time was settled, if it
+    /// had to be.
+    pub replacement: Option<ReplacementOutcome>,
+
+    /// How the engine submitted the transaction and how long its receipt
+    /// took, if the engine submitted it.
+    pub submission: Option<Submission>,
+}
+
+impl ActionMetrics {
+    /// Build metrics from the result of an action.
+    #[must_use]
+    pub fn from_result(
+        plugin_id: impl Into<String>,
+        action_id: impl Into<String>,
+        wallet_id: impl Into<String>,
+        result: &ActionResult,
+    ) -> Self {
+        Self {
+            plugin_id: plugin_id.into(),
+            action_id: action_id.into(),
+            wallet_id: wallet_id.into(),
+            status: result.status,
+            duration_ms: result.duration_ms,
+            gas_used: result.gas_used,
+            gas_estimate: result.gas_estimate,
+            gas_cost_wei: result.gas_cost_wei(),
+            replacement: result.replacement.map(|r| r.outcome),
+            submission: result.submission,
+        }
+    }
 }
 
 /// Snapshot of fleet-wide metrics.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct FleetSnapshot {
+    /// Fleet this snapshot is of, `None` for a single unnamed fleet or a
+    /// roll-up of several.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fleet_id: Option<String>,
+
     /// When this snapshot was taken.
     pub timestamp: Option<DateTime<Utc>>,
 
@@ -82,6 +170,13 @@ pub struct FleetSnapshot {
     /// Wallets currently AFK.
     pub afk_wallets: usize,
 
+    /// Wallets that reached a budget cap, as opposed to tripped ones.
+    pub budget_exhausted_wallets: usize,
+
+    /// Whether the fleet as a whole reached a budget cap. In a roll-up,
+    /// whether any fleet did.
+    pub fleet_budget_exhausted: bool,
+
     /// Total actions executed since startup.
     pub total_actions: u64,
 
@@ -91,49 +186,322 @@ pub struct FleetSnapshot {
     /// Failed actions since startup.
     pub failed_actions: u64,
 
+    /// Actions by outcome.
+    pub actions_by_status: HashMap<ActionStatus, u64>,
+
+    /// Total gas cost in wei of actions whose cost is known.
+    pub total_gas_cost_wei: u128,
+
+    /// Execution errors by class.
+    pub errors_by_class: HashMap<ErrorClass, u64>,
+
+    /// Actions whose transaction had to be replaced, by outcome.
+    pub replacements_by_outcome: HashMap<ReplacementOutcome, u64>,
+
+    /// Transactions the engine submitted, by path.
+    #[serde(default)]
+    pub submissions_by_path: HashMap<SubmissionPath, u64>,
+
     /// Actions by plugin.
     pub actions_by_plugin: HashMap<String, u64>,
 
     /// Actions by action type.
     pub actions_by_type: HashMap<String, u64>,
+
+    /// Actions by wallet.
+    pub actions_by_wallet: HashMap<String, u64>,
+
+    /// Successful actions by wallet.
+    #[serde(default)]
+    pub successes_by_wallet: HashMap<String, u64>,
+
+    /// Failed actions by wallet.
+    #[serde(default)]
+    pub failures_by_wallet: HashMap<String, u64>,
+
+    /// Decisions skipped because the plugin could not possibly act, by
+    /// plugin.
+    pub prefiltered_by_plugin: HashMap<String, u64>,
+
+    /// Plugin calls that ran past their timeout and were cancelled, by
+    /// plugin.
+    #[serde(default)]
+    pub timeouts_by_plugin: HashMap<String, u64>,
+
+    /// Health of each plugin, with those disabled for failing too often or
+    /// by an operator.
+    #[serde(default)]
+    pub plugins: Vec<PluginHealthStatus>,
+
+    /// Usage of each wallet group's action limit.
+    pub group_stats: Vec<GroupStats>,
+
+    /// Requests and assigned wallets of each RPC endpoint.
+    pub endpoint_stats: Vec<EndpointStats>,
+
+    /// Progress of the ramp-up of wallets overdue at startup, while it runs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cold_start: Option<RampProgress>,
+
+    /// Actions by UTC hour of the day, midnight first.
+    #[serde(default)]
+    pub actions_by_hour: [u64; 24],
+
+    /// Concentration of recent actions in time. In a roll-up, that of the
+    /// most concentrated fleet, flagged if any fleet is.
+    #[serde(default)]
+    pub correlation: ActivityCorrelation,
+
+    /// DATA paid and received, and gas paid, as the service's ledger counts
+    /// them.
+    #[serde(default)]
+    pub pnl: PnlSummary,
+
+    /// Degradation tier the RPC endpoints put the fleet in. In a roll-up,
+    /// that of the most degraded fleet.
+    #[serde(default)]
+    pub degradation: DegradationStatus,
+}
+
+impl FleetSnapshot {
+    /// Combine the snapshots of several fleets into one.
+    ///
+    /// Counts, profit and loss, and per-key maps are summed, as are the
+    /// cold-start ramps that are still running, which end with the last of
+    /// them. The correlation
+    /// is that of the most concentrated fleet, and the degradation that of
+    /// the most degraded. Wallet ids, group names and
+    /// plugin health only need to be unique within a fleet, so per-wallet
+    /// counts, group stats and plugin health are keyed `<fleet>/<name>` in the
+    /// roll-up. The fleets share one
+    /// provider pool, so endpoint stats are taken from the first snapshot
+    /// rather than added up.
+    #[must_use]
+    pub fn roll_up(snapshots: &[Self]) -> Self {
+        let mut total = Self {
+            endpoint_stats: snapshots
+                .first()
+                .map(|s| s.endpoint_stats.clone())
+                .unwrap_or_default(),
+            degradation: snapshots
+                .first()
+                .map(|s| s.degradation)
+                .unwrap_or_default(),
+            ..Self::default()
+        };
+        for snapshot in snapshots {
+            let scoped = |name: &str| {
+                snapshot
+                    .fleet_id
+                    .as_ref()
+                    .map_or_else(|| name.to_string(), |fleet| format!("{fleet}/{name}"))
+            };
+            total.timestamp = total.timestamp.max(snapshot.timestamp);
+            total.active_wallets += snapshot.active_wallets;
+            total.tripped_wallets += snapshot.tripped_wallets;
+            total.afk_wallets += snapshot.afk_wallets;
+            total.budget_exhausted_wallets += snapshot.budget_exhausted_wallets;
+            total.fleet_budget_exhausted |= snapshot.fleet_budget_exhausted;
+            total.total_actions += snapshot.total_actions;
+            total.successful_actions += snapshot.successful_actions;
+            total.failed_actions += snapshot.failed_actions;
+            total.total_gas_cost_wei += snapshot.total_gas_cost_wei;
+            if let Some(ramp) = snapshot.cold_start {
+                total.cold_start = Some(total.cold_start.map_or(ramp, |sum| RampProgress {
+                    wallets: sum.wallets + ramp.wallets,
+                    started: sum.started + ramp.started,
+                    ends_at: sum.ends_at.max(ramp.ends_at),
+                }));
+            }
+            for (hour, count) in total.actions_by_hour.iter_mut().zip(snapshot.actions_by_hour) {
+                *hour += count;
+            }
+            let flagged = total.correlation.flagged || snapshot.correlation.flagged;
+            if snapshot.correlation.concentration > total.correlation.concentration {
+                total.correlation = snapshot.correlation;
+            }
+            total.correlation.flagged = flagged;
+            if snapshot.degradation.tier > total.degradation.tier {
+                total.degradation = snapshot.degradation;
+            }
+            total.pnl.add(&snapshot.pnl, scoped);
+            add_counts(&mut total.actions_by_status, &snapshot.actions_by_status);
+            add_counts(&mut total.errors_by_class, &snapshot.errors_by_class);
+            add_counts(
+                &mut total.replacements_by_outcome,
+                &snapshot.replacements_by_outcome,
+            );
+            add_counts(&mut total.submissions_by_path, &snapshot.submissions_by_path);
+            add_counts(&mut total.actions_by_plugin, &snapshot.actions_by_plugin);
+            add_counts(&mut total.actions_by_type, &snapshot.actions_by_type);
+            add_counts(
+                &mut total.prefiltered_by_plugin,
+                &snapshot.prefiltered_by_plugin,
+            );
+            add_counts(&mut total.timeouts_by_plugin, &snapshot.timeouts_by_plugin);
+            for (into, from) in [
+                (&mut total.actions_by_wallet, &snapshot.actions_by_wallet),
+                (&mut total.successes_by_wallet, &snapshot.successes_by_wallet),
+                (&mut total.failures_by_wallet, &snapshot.failures_by_wallet),
+            ] {
+                for (wallet, count) in from {
+                    *into.entry(scoped(wallet)).or_default() += count;
+                }
+            }
+            total
+                .group_stats
+                .extend(snapshot.group_stats.iter().map(|stats| GroupStats {
+                    group: scoped(&stats.group),
+                    ..stats.clone()
+                }));
+            total
+                .plugins
+                .extend(snapshot.plugins.iter().map(|health| PluginHealthStatus {
+                    plugin_id: scoped(&health.plugin_id),
+                    ..health.clone()
+                }));
+        }
+        total
+    }
+}
+
+/// Add every count in `from` to `into`.
+fn add_counts<K: Eq + Hash + Clone>(into: &mut HashMap<K, u64>, from: &HashMap<K, u64>) {
+    for (key, count) in from {
+        *into.entry(key.clone()).or_default() += count;
+    }
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
 // FLEET METRICS
 // ═══════════════════════════════════════════════════════════════════════════════
 
+/// Number of recent durations and gas readings kept for averages and
+/// percentiles.
+const RECENT_SAMPLES: usize = 1000;
+
+/// Number of recent action times kept for the activity correlation.
+const RECENT_ACTION_TIMES: usize = 5000;
+
 /// In-memory metrics collector.
 ///
-/// Collects metrics during operation and provides summary snapshots.
-/// This is a simple implementation suitable for small fleets. For larger
-/// deployments, connect to a proper metrics system (Prometheus, etc.).
-#[derive(Debug, Default)]
+/// Collects metrics during operation and provides summary snapshots. All
+/// recording methods take `&self`, so one collector can be shared between
+/// concurrently executing wallets without a lock around it: counters are
+/// atomics, per-key counts live in sharded maps, and recent samples in
+/// fixed-size ring buffers.
+///
+/// Readers never block writers. A snapshot taken while actions are being
+/// recorded may count an action in one field and not yet in another, but
+/// every count only grows.
+///
+/// For larger deployments, connect to a proper metrics system (Prometheus,
+/// etc.).
+#[derive(Debug)]
 pub struct FleetMetrics {
     /// Total actions executed.
-    total_actions: u64,
+    total_actions: AtomicU64,
 
     /// Successful actions.
-    successful_actions: u64,
+    successful_actions: AtomicU64,
 
     /// Failed actions.
-    failed_actions: u64,
+    failed_actions: AtomicU64,
+
+    /// Actions by status.
+    by_status: Counts<ActionStatus>,
+
+    /// Total gas cost in wei. There is no stable `AtomicU128`; the lock is
+    /// held for a single addition.
+    total_gas_cost_wei: Mutex<u128>,
+
+    /// Execution errors by class.
+    by_error_class: Counts<ErrorClass>,
+
+    /// Replaced transactions by outcome.
+    by_replacement: Counts<ReplacementOutcome>,
+
+    /// Transactions submitted by the engine, by path.
+    by_submission: Counts<SubmissionPath>,
 
     /// Actions by plugin ID.
-    by_plugin: HashMap<String, u64>,
+    by_plugin: Counts<String>,
 
     /// Actions by action ID.
-    by_action: HashMap<String, u64>,
+    by_action: Counts<String>,
 
     /// Actions by wallet ID.
-    by_wallet: HashMap<String, u64>,
+    by_wallet: Counts<String>,
+
+    /// Successful actions by wallet ID.
+    successes_by_wallet: Counts<String>,
+
+    /// Failed actions by wallet ID.
+    failures_by_wallet: Counts<String>,
+
+    /// Prefiltered decisions by plugin ID.
+    prefiltered: Counts<String>,
+
+    /// Cancelled plugin calls by plugin.
+    timeouts: Counts<String>,
 
     /// Recent action durations (for percentile calculation).
-    /// Limited to last 1000 entries. Uses `VecDeque` for O(1) front removal.
-    recent_durations: VecDeque<u64>,
+    recent_durations: Samples,
 
     /// Recent gas usage.
-    /// Limited to last 1000 entries. Uses `VecDeque` for O(1) front removal.
-    recent_gas: VecDeque<u64>,
+    recent_gas: Samples,
+
+    /// Recent gas usage as a percentage of the estimate.
+    recent_gas_of_estimate: Samples,
+
+    /// Recent receipt latencies of realtime submissions.
+    recent_realtime_latency: Samples,
+
+    /// Recent receipt latencies of standard submissions.
+    recent_standard_latency: Samples,
+
+    /// Actions by UTC hour of the day.
+    by_hour: [AtomicU64; 24],
+
+    /// Unix times in seconds of recent actions.
+    recent_action_times: Samples,
+
+    /// How recent actions are checked for lockstep.
+    correlation: CorrelationSettings,
+
+    /// Source of the action times.
+    clock: SharedClock,
+}
+
+impl Default for FleetMetrics {
+    fn default() -> Self {
+        Self {
+            total_actions: AtomicU64::new(0),
+            successful_actions: AtomicU64::new(0),
+            failed_actions: AtomicU64::new(0),
+            by_status: Counts::default(),
+            total_gas_cost_wei: Mutex::new(0),
+            by_error_class: Counts::default(),
+            by_replacement: Counts::default(),
+            by_submission: Counts::default(),
+            by_plugin: Counts::default(),
+            by_action: Counts::default(),
+            by_wallet: Counts::default(),
+            successes_by_wallet: Counts::default(),
+            failures_by_wallet: Counts::default(),
+            prefiltered: Counts::default(),
+            timeouts: Counts::default(),
+            recent_durations: Samples::new(RECENT_SAMPLES),
+            recent_gas: Samples::new(RECENT_SAMPLES),
+            recent_gas_of_estimate: Samples::new(RECENT_SAMPLES),
+            recent_realtime_latency: Samples::new(RECENT_SAMPLES),
+            recent_standard_latency: Samples::new(RECENT_SAMPLES),
+            by_hour: std::array::from_fn(|_| AtomicU64::new(0)),
+            recent_action_times: Samples::new(RECENT_ACTION_TIMES),
+            correlation: CorrelationSettings::default(),
+            clock: system_clock(),
+        }
+    }
 }
 
 impl FleetMetrics {
@@ -143,150 +511,475 @@ impl FleetMetrics {
         Self::default()
     }
 
-    /// Record metrics for an action execution.
-    pub fn record_action(&mut self, metrics: ActionMetrics) {
-        self.total_actions += 1;
+    /// Use the given clock to time actions.
+    #[must_use]
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
 
-        if metrics.success {
-            self.successful_actions += 1;
-        } else {
-            self.failed_actions += 1;
+    /// Check recent actions for lockstep with the given settings.
+    #[must_use]
+    pub const fn with_correlation(mut self, settings: CorrelationSettings) -> Self {
+        self.correlation = settings;
+        self
+    }
+
+    /// Record metrics for an action execution.
+    ///
+    /// Skipped and simulated actions count towards the totals but are
+    /// neither successes nor failures. Every action but a skipped one is
+    /// timed for the activity correlation.
+    pub fn record_action(&self, metrics: ActionMetrics) {
+        self.total_actions.fetch_add(1, Ordering::Relaxed);
+        if metrics.status != ActionStatus::Skipped {
+            self.record_activity(self.clock.now());
         }
 
-        *self.by_plugin.entry(metrics.plugin_id).or_insert(0) += 1;
-        *self.by_action.entry(metrics.action_id).or_insert(0) += 1;
-        *self.by_wallet.entry(metrics.wallet_id).or_insert(0) += 1;
+        if metrics.status.is_success() {
+            self.successful_actions.fetch_add(1, Ordering::Relaxed);
+            self.successes_by_wallet.increment(metrics.wallet_id.clone());
+        } else if metrics.status.is_failure() {
+            self.failed_actions.fetch_add(1, Ordering::Relaxed);
+            self.failures_by_wallet.increment(metrics.wallet_id.clone());
+        }
+        self.by_status.increment(metrics.status);
+        if let Some(outcome) = metrics.replacement {
+            self.by_replacement.increment(outcome);
+        }
+        if let Some(submission) = metrics.submission {
+            self.by_submission.increment(submission.path);
+            if let Some(latency_ms) = submission.receipt_latency_ms {
+                self.receipt_latencies(submission.path).push(latency_ms);
+            }
+        }
 
-        // Keep recent durations (ring buffer with O(1) operations)
-        if self.recent_durations.len() >= 1000 {
-            self.recent_durations.pop_front();
+        if let Some(cost) = metrics.gas_cost_wei {
+            let mut total = lock(&self.total_gas_cost_wei);
+            *total = total.saturating_add(cost);
         }
-        self.recent_durations.push_back(metrics.duration_ms);
 
-        // Keep recent gas usage (ring buffer with O(1) operations)
+        self.by_plugin.increment(metrics.plugin_id);
+        self.by_action.increment(metrics.action_id);
+        self.by_wallet.increment(metrics.wallet_id);
+
+        if let Some(duration_ms) = metrics.duration_ms {
+            self.recent_durations.push(duration_ms);
+        }
         if let Some(gas) = metrics.gas_used {
-            if self.recent_gas.len() >= 1000 {
-                self.recent_gas.pop_front();
-            }
-            self.recent_gas.push_back(gas);
+            self.recent_gas.push(gas);
+        }
+        if let (Some(used), Some(estimate)) = (metrics.gas_used, metrics.gas_estimate)
+            && estimate > 0
+        {
+            let pct = u128::from(used) * 100 / u128::from(estimate);
+            self.recent_gas_of_estimate.push(u64::try_from(pct).unwrap_or(u64::MAX));
         }
     }
 
+    /// Record the result of an action execution.
+    ///
+    /// Status, duration and gas are taken from the result.
+    pub fn record_result(
+        &self,
+        plugin_id: &str,
+        action_id: &str,
+        wallet_id: &str,
+        result: &ActionResult,
+    ) {
+        self.record_action(ActionMetrics::from_result(plugin_id, action_id, wallet_id, result));
+    }
+
+    /// Record that the fleet acted at `at`.
+    fn record_activity(&self, at: DateTime<Utc>) {
+        self.by_hour[at.hour() as usize].fetch_add(1, Ordering::Relaxed);
+        self.recent_action_times
+            .push(u64::try_from(at.timestamp()).unwrap_or_default());
+    }
+
+    /// Record an action execution that failed with an error of `class`.
+    ///
+    /// Counted separately from the action itself, which is recorded through
+    /// [`record_action`](Self::record_action) as usual.
+    pub fn record_error(&self, class: ErrorClass) {
+        self.by_error_class.increment(class);
+    }
+
+    /// Record that a plugin was not asked to decide for a wallet, as it
+    /// could not possibly act.
+    pub fn record_prefiltered(&self, plugin_id: &str) {
+        self.prefiltered.increment(plugin_id.to_string());
+    }
+
+    /// Record that a plugin call ran past its timeout and was cancelled.
+    ///
+    /// The error it ends with is recorded through
+    /// [`record_error`](Self::record_error) as usual.
+    pub fn record_timeout(&self, plugin_id: &str) {
+        self.timeouts.increment(plugin_id.to_string());
+    }
+
     /// Get total actions executed.
     #[must_use]
-    pub const fn total_actions(&self) -> u64 {
-        self.total_actions
+    pub fn total_actions(&self) -> u64 {
+        self.total_actions.load(Ordering::Relaxed)
     }
 
     /// Get successful action count.
     #[must_use]
-    pub const fn successful_actions(&self) -> u64 {
-        self.successful_actions
+    pub fn successful_actions(&self) -> u64 {
+        self.successful_actions.load(Ordering::Relaxed)
     }
 
     /// Get failed action count.
     #[must_use]
-    pub const fn failed_actions(&self) -> u64 {
-        self.failed_actions
+    pub fn failed_actions(&self) -> u64 {
+        self.failed_actions.load(Ordering::Relaxed)
+    }
+
+    /// Get the number of actions with the given status.
+    #[must_use]
+    pub fn actions_with_status(&self, status: ActionStatus) -> u64 {
+        self.by_status.get(&status)
+    }
+
+    /// Get the number of replaced transactions settled with `outcome`.
+    #[must_use]
+    pub fn replacements_with_outcome(&self, outcome: ReplacementOutcome) -> u64 {
+        self.by_replacement.get(&outcome)
+    }
+
+    /// Get the number of transactions the engine submitted through `path`.
+    #[must_use]
+    pub fn submissions_with_path(&self, path: SubmissionPath) -> u64 {
+        self.by_submission.get(&path)
+    }
+
+    /// Get the number of execution errors of the given class.
+    #[must_use]
+    pub fn errors_with_class(&self, class: ErrorClass) -> u64 {
+        self.by_error_class.get(&class)
+    }
+
+    /// Get total gas cost in wei of actions whose cost is known.
+    #[must_use]
+    pub fn total_gas_cost_wei(&self) -> u128 {
+        *lock(&self.total_gas_cost_wei)
     }
 
     /// Get success rate as a percentage (0-100).
+    ///
+    /// Only actions that succeeded or failed are considered.
     #[must_use]
     #[allow(clippy::cast_precision_loss)] // Acceptable for metrics display
     pub fn success_rate(&self) -> f64 {
-        if self.total_actions == 0 {
+        let successful = self.successful_actions();
+        let attempted = successful + self.failed_actions();
+        if attempted == 0 {
             100.0
         } else {
-            (self.successful_actions as f64 / self.total_actions as f64) * 100.0
+            (successful as f64 / attempted as f64) * 100.0
         }
     }
 
     /// Get actions count for a specific plugin.
     #[must_use]
     pub fn actions_for_plugin(&self, plugin_id: &str) -> u64 {
-        self.by_plugin.get(plugin_id).copied().unwrap_or(0)
+        self.by_plugin.get(plugin_id)
+    }
+
+    /// Get the number of prefiltered decisions for a specific plugin.
+    #[must_use]
+    pub fn prefiltered_for_plugin(&self, plugin_id: &str) -> u64 {
+        self.prefiltered.get(plugin_id)
+    }
+
+    /// Get the number of cancelled calls of a specific plugin.
+    #[must_use]
+    pub fn timeouts_for_plugin(&self, plugin_id: &str) -> u64 {
+        self.timeouts.get(plugin_id)
     }
 
     /// Get actions count for a specific wallet.
     #[must_use]
     pub fn actions_for_wallet(&self, wallet_id: &str) -> u64 {
-        self.by_wallet.get(wallet_id).copied().unwrap_or(0)
+        self.by_wallet.get(wallet_id)
+    }
+
+    /// Get successful action count for a specific wallet.
+    #[must_use]
+    pub fn successes_for_wallet(&self, wallet_id: &str) -> u64 {
+        self.successes_by_wallet.get(wallet_id)
+    }
+
+    /// Get failed action count for a specific wallet.
+    #[must_use]
+    pub fn failures_for_wallet(&self, wallet_id: &str) -> u64 {
+        self.failures_by_wallet.get(wallet_id)
     }
 
     /// Get average action duration in milliseconds.
     #[must_use]
-    #[allow(clippy::cast_precision_loss)] // Acceptable for metrics display
     pub fn avg_duration_ms(&self) -> f64 {
-        if self.recent_durations.is_empty() {
-            0.0
-        } else {
-            self.recent_durations.iter().sum::<u64>() as f64
-                / self.recent_durations.len() as f64
-        }
+        average(&self.recent_durations.values())
     }
 
     /// Get p50 (median) action duration in milliseconds.
     #[must_use]
     pub fn p50_duration_ms(&self) -> u64 {
-        percentile_deque(&self.recent_durations, 50)
+        percentile(self.recent_durations.values(), 50)
     }
 
     /// Get p95 action duration in milliseconds.
     #[must_use]
     pub fn p95_duration_ms(&self) -> u64 {
-        percentile_deque(&self.recent_durations, 95)
+        percentile(self.recent_durations.values(), 95)
     }
 
     /// Get p99 action duration in milliseconds.
     #[must_use]
     pub fn p99_duration_ms(&self) -> u64 {
-        percentile_deque(&self.recent_durations, 99)
+        percentile(self.recent_durations.values(), 99)
+    }
+
+    /// Get p50 (median) submission-to-receipt latency in milliseconds of
+    /// transactions submitted through `path`.
+    #[must_use]
+    pub fn p50_receipt_latency_ms(&self, path: SubmissionPath) -> u64 {
+        percentile(self.receipt_latencies(path).values(), 50)
+    }
+
+    /// Get p95 submission-to-receipt latency in milliseconds of
+    /// transactions submitted through `path`.
+    #[must_use]
+    pub fn p95_receipt_latency_ms(&self, path: SubmissionPath) -> u64 {
+        percentile(self.receipt_latencies(path).values(), 95)
+    }
+
+    /// Recent receipt latencies of submissions through `path`.
+    const fn receipt_latencies(&self, path: SubmissionPath) -> &Samples {
+        match path {
+            SubmissionPath::Realtime => &self.recent_realtime_latency,
+            SubmissionPath::Standard => &self.recent_standard_latency,
+        }
     }
 
     /// Get average gas used per action.
     #[must_use]
-    #[allow(clippy::cast_precision_loss)] // Acceptable for metrics display
     pub fn avg_gas_used(&self) -> f64 {
-        if self.recent_gas.is_empty() {
-            0.0
-        } else {
-            self.recent_gas.iter().sum::<u64>() as f64 / self.recent_gas.len() as f64
-        }
+        average(&self.recent_gas.values())
+    }
+
+    /// Get average gas used as a percentage of the estimate, over recent
+    /// actions whose gas was estimated.
+    ///
+    /// Well below 100 means the safety margin on estimates can shrink; near
+    /// or above it, estimates run short.
+    #[must_use]
+    pub fn avg_gas_used_of_estimate_pct(&self) -> f64 {
+        average(&self.recent_gas_of_estimate.values())
+    }
+
+    /// Get the number of actions taken in each UTC hour of the day,
+    /// midnight first.
+    #[must_use]
+    pub fn actions_by_hour(&self) -> [u64; 24] {
+        std::array::from_fn(|hour| self.by_hour[hour].load(Ordering::Relaxed))
+    }
+
+    /// Measure how recent actions concentrate in time, see
+    /// [`ActivityCorrelation`].
+    ///
+    /// Only the last 5000 actions are kept, so a busy fleet is judged on a
+    /// shorter window than configured.
+    #[must_use]
+    pub fn correlation(&self) -> ActivityCorrelation {
+        let times = self.recent_action_times.values().into_iter().filter_map(|secs| {
+            Utc.timestamp_opt(i64::try_from(secs).ok()?, 0).single()
+        });
+        ActivityCorrelation::measure(times, self.clock.now(), &self.correlation)
     }
 
     /// Create a snapshot of current metrics.
+    ///
+    /// Writers are not stopped, so the fields are read one after another
+    /// (see the type docs).
     #[must_use]
     pub fn snapshot(&self) -> FleetSnapshot {
         FleetSnapshot {
+            fleet_id: None, // Filled in by caller
             timestamp: Some(Utc::now()),
             active_wallets: 0, // Filled in by caller
             tripped_wallets: 0,
             afk_wallets: 0,
-            total_actions: self.total_actions,
-            successful_actions: self.successful_actions,
-            failed_actions: self.failed_actions,
-            actions_by_plugin: self.by_plugin.clone(),
-            actions_by_type: self.by_action.clone(),
+            budget_exhausted_wallets: 0,
+            fleet_budget_exhausted: false,
+            total_actions: self.total_actions(),
+            successful_actions: self.successful_actions(),
+            failed_actions: self.failed_actions(),
+            actions_by_status: self.by_status.to_map(),
+            total_gas_cost_wei: self.total_gas_cost_wei(),
+            errors_by_class: self.by_error_class.to_map(),
+            replacements_by_outcome: self.by_replacement.to_map(),
+            submissions_by_path: self.by_submission.to_map(),
+            actions_by_plugin: self.by_plugin.to_map(),
+            actions_by_type: self.by_action.to_map(),
+            actions_by_wallet: self.by_wallet.to_map(),
+            successes_by_wallet: self.successes_by_wallet.to_map(),
+            failures_by_wallet: self.failures_by_wallet.to_map(),
+            prefiltered_by_plugin: self.prefiltered.to_map(),
+            timeouts_by_plugin: self.timeouts.to_map(),
+            plugins: Vec::new(), // Filled in by caller
+            group_stats: Vec::new(), // Filled in by caller
+            endpoint_stats: Vec::new(), // Filled in by caller
+            cold_start: None, // Filled in by caller
+            actions_by_hour: self.actions_by_hour(),
+            correlation: self.correlation(),
+            pnl: PnlSummary::default(), // Filled in by caller
+            degradation: DegradationStatus::default(), // Filled in by caller
         }
     }
 
     /// Reset all metrics.
-    pub fn reset(&mut self) {
-        *self = Self::new();
+    ///
+    /// Actions recorded while resetting may be partly kept.
+    pub fn reset(&self) {
+        self.total_actions.store(0, Ordering::Relaxed);
+        self.successful_actions.store(0, Ordering::Relaxed);
+        self.failed_actions.store(0, Ordering::Relaxed);
+        self.by_status.clear();
+        *lock(&self.total_gas_cost_wei) = 0;
+        self.by_error_class.clear();
+        self.by_replacement.clear();
+        self.by_submission.clear();
+        self.by_plugin.clear();
+        self.by_action.clear();
+        self.by_wallet.clear();
+        self.successes_by_wallet.clear();
+        self.failures_by_wallet.clear();
+        self.prefiltered.clear();
+        self.timeouts.clear();
+        self.recent_durations.clear();
+        self.recent_gas.clear();
+        self.recent_gas_of_estimate.clear();
+        self.recent_realtime_latency.clear();
+        self.recent_standard_latency.clear();
+        for hour in &self.by_hour {
+            hour.store(0, Ordering::Relaxed);
+        }
+        self.recent_action_times.clear();
     }
 }
 
-/// Calculate percentile of a dataset stored in a `VecDeque`.
-fn percentile_deque(data: &VecDeque<u64>, p: usize) -> u64 {
+/// Lock `mutex`, ignoring poisoning: a panicking writer cannot leave a
+/// plain number half-updated.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Counts per key, in a sharded map.
+///
+/// Keys seen before are counted under a shard read lock; only the first
+/// count of a key takes a shard write lock.
+#[derive(Debug)]
+struct Counts<K: Eq + Hash>(DashMap<K, AtomicU64>);
+
+impl<K: Eq + Hash> Default for Counts<K> {
+    fn default() -> Self {
+        Self(DashMap::new())
+    }
+}
+
+impl<K: Eq + Hash + Clone> Counts<K> {
+    fn increment(&self, key: K) {
+        if let Some(count) = self.0.get(&key) {
+            count.fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+        self.0.entry(key).or_default().fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn get<Q>(&self, key: &Q) -> u64
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.0
+            .get(key)
+            .map_or(0, |count| count.load(Ordering::Relaxed))
+    }
+
+    fn to_map(&self) -> HashMap<K, u64> {
+        self.0
+            .iter()
+            .map(|entry| (entry.key().clone(), entry.value().load(Ordering::Relaxed)))
+            .collect()
+    }
+
+    fn clear(&self) {
+        self.0.clear();
+    }
+}
+
+/// The last `capacity` values pushed, in a fixed-size ring buffer.
+///
+/// Writers claim a slot with one atomic increment and overwrite it, so
+/// pushing is O(1) and never waits. A reader racing a writer may see a
+/// slot's previous value, which is fine for averages and percentiles.
+#[derive(Debug)]
+struct Samples {
+    /// Ring buffer slots.
+    slots: Box<[AtomicU64]>,
+    /// Number of values pushed; the next slot is this modulo the capacity.
+    pushed: AtomicUsize,
+}
+
+impl Samples {
+    fn new(capacity: usize) -> Self {
+        Self {
+            slots: (0..capacity).map(|_| AtomicU64::new(0)).collect(),
+            pushed: AtomicUsize::new(0),
+        }
+    }
+
+    fn push(&self, value: u64) {
+        let index = self.pushed.fetch_add(1, Ordering::Relaxed) % self.slots.len();
+        self.slots[index].store(value, Ordering::Relaxed);
+    }
+
+    /// The values currently held, in no particular order.
+    fn values(&self) -> Vec<u64> {
+        let len = self.pushed.load(Ordering::Relaxed).min(self.slots.len());
+        self.slots[..len]
+            .iter()
+            .map(|slot| slot.load(Ordering::Relaxed))
+            .collect()
+    }
+
+    fn clear(&self) {
+        self.pushed.store(0, Ordering::Relaxed);
+    }
+}
+
+/// Mean of `data`, or `0.0` if it is empty.
+#[allow(clippy::cast_precision_loss)] // Acceptable for metrics display
+fn average(data: &[u64]) -> f64 {
     if data.is_empty() {
-        return 0;
+        0.0
+    } else {
+        data.iter().sum::<u64>() as f64 / data.len() as f64
     }
+}
 
-    let mut sorted: Vec<u64> = data.iter().copied().collect();
-    sorted.sort_unstable();
+/// Calculate the `p`th percentile of `data`.
+fn percentile(mut data: Vec<u64>, p: usize) -> u64 {
+    if data.is_empty() {
+        return 0;
+    }
 
-    let idx = (p * sorted.len() / 100).min(sorted.len() - 1);
-    sorted[idx]
+    data.sort_unstable();
+    let idx = (p * data.len() / 100).min(data.len() - 1);
+    data[idx]
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -294,23 +987,36 @@ fn percentile_deque(data: &VecDeque<u64>, p: usize) -> u64 {
 // ═══════════════════════════════════════════════════════════════════════════════
 
 #[cfg(test)]
+#[allow(clippy::unwrap_used)]
 mod tests {
+    use alloy::primitives::U256;
+
     use super::*;
+    use crate::plugins::{HealthSettings, PluginHealth};
+    use crate::safety::DegradationTier;
 
     fn sample_action(success: bool, duration_ms: u64) -> ActionMetrics {
         ActionMetrics {
             plugin_id: "test".to_string(),
             action_id: "test.action".to_string(),
             wallet_id: "wallet_1".to_string(),
-            success,
-            duration_ms,
+            status: if success {
+                ActionStatus::Succeeded
+            } else {
+                ActionStatus::Reverted
+            },
+            duration_ms: Some(duration_ms),
             gas_used: Some(100_000),
+            gas_estimate: None,
+            gas_cost_wei: None,
+            replacement: None,
+            submission: None,
         }
     }
 
     #[test]
     fn counts_actions() {
-        let mut metrics = FleetMetrics::new();
+        let metrics = FleetMetrics::new();
 
         metrics.record_action(sample_action(true, 100));
         metrics.record_action(sample_action(true, 100));
@@ -323,7 +1029,7 @@ mod tests {
 
     #[test]
     fn success_rate() {
-        let mut metrics = FleetMetrics::new();
+        let metrics = FleetMetrics::new();
 
         metrics.record_action(sample_action(true, 100));
         metrics.record_action(sample_action(true, 100));
@@ -342,7 +1048,7 @@ mod tests {
 
     #[test]
     fn tracks_by_plugin() {
-        let mut metrics = FleetMetrics::new();
+        let metrics = FleetMetrics::new();
 
         let mut action = sample_action(true, 100);
         action.plugin_id = "plugin_a".to_string();
@@ -358,9 +1064,26 @@ mod tests {
         assert_eq!(metrics.actions_for_plugin("unknown"), 0);
     }
 
+    #[test]
+    fn counts_prefiltered_by_plugin() {
+        let metrics = FleetMetrics::new();
+
+        metrics.record_prefiltered("plugin_a");
+        metrics.record_prefiltered("plugin_a");
+
+        assert_eq!(metrics.prefiltered_for_plugin("plugin_a"), 2);
+        assert_eq!(metrics.prefiltered_for_plugin("plugin_b"), 0);
+        // Prefiltered decisions are not actions
+        assert_eq!(metrics.total_actions(), 0);
+        assert_eq!(metrics.snapshot().prefiltered_by_plugin["plugin_a"], 2);
+
+        metrics.reset();
+        assert_eq!(metrics.prefiltered_for_plugin("plugin_a"), 0);
+    }
+
     #[test]
     fn duration_percentiles() {
-        let mut metrics = FleetMetrics::new();
+        let metrics = FleetMetrics::new();
 
         // Add 100 actions with durations 1-100ms
         for i in 1..=100 {
@@ -369,16 +1092,16 @@ mod tests {
 
         // P50 should be around 50
         let p50 = metrics.p50_duration_ms();
-        assert!(p50 >= 45 && p50 <= 55, "p50 was {p50}");
+        assert!((45..=55).contains(&p50), "p50 was {p50}");
 
         // P95 should be around 95
         let p95 = metrics.p95_duration_ms();
-        assert!(p95 >= 90 && p95 <= 100, "p95 was {p95}");
+        assert!((90..=100).contains(&p95), "p95 was {p95}");
     }
 
     #[test]
     fn snapshot_captures_state() {
-        let mut metrics = FleetMetrics::new();
+        let metrics = FleetMetrics::new();
         metrics.record_action(sample_action(true, 100));
         metrics.record_action(sample_action(false, 200));
 
@@ -389,4 +1112,284 @@ mod tests {
         assert_eq!(snapshot.successful_actions, 1);
         assert_eq!(snapshot.failed_actions, 1);
     }
+
+    #[test]
+    fn records_results() {
+        let metrics = FleetMetrics::new();
+        let success = ActionResult::success_with_gas(alloy::primitives::TxHash::ZERO, 100_000)
+            .with_effective_gas_price(2)
+            .with_duration(std::time::Duration::from_millis(300));
+
+        metrics.record_result("test", "test.action", "wallet_1", &success);
+        metrics.record_result("test", "test.action", "wallet_1", &ActionResult::simulated());
+        metrics.record_result("test", "test.action", "wallet_1", &ActionResult::skipped("idle"));
+
+        assert_eq!(metrics.total_actions(), 3);
+        assert_eq!(metrics.successful_actions(), 1);
+        assert_eq!(metrics.failed_actions(), 0);
+        assert_eq!(metrics.actions_with_status(ActionStatus::Simulated), 1);
+        assert!((metrics.success_rate() - 100.0).abs() < 0.01);
+        assert_eq!(metrics.total_gas_cost_wei(), 200_000);
+        // Only measured durations count
+        assert_eq!(metrics.p50_duration_ms(), 300);
+        assert!((metrics.avg_gas_used() - 100_000.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn counts_replacements_by_outcome() {
+        let metrics = FleetMetrics::new();
+        let tx_hash = alloy::primitives::TxHash::ZERO;
+        let replaced = ActionResult::success(tx_hash)
+            .with_replacement(ReplacementOutcome::ReplacementMined, 1);
+        let cancelled = ActionResult::dropped(tx_hash, "cancelled")
+            .with_replacement(ReplacementOutcome::Cancelled, 3);
+
+        metrics.record_result("test", "test.action", "wallet_1", &replaced);
+        metrics.record_result("test", "test.action", "wallet_1", &cancelled);
+        metrics.record_result("test", "test.action", "wallet_1", &ActionResult::success(tx_hash));
+
+        assert_eq!(metrics.replacements_with_outcome(ReplacementOutcome::ReplacementMined), 1);
+        assert_eq!(metrics.replacements_with_outcome(ReplacementOutcome::Cancelled), 1);
+        assert_eq!(metrics.replacements_with_outcome(ReplacementOutcome::Abandoned), 0);
+        assert_eq!(metrics.snapshot().replacements_by_outcome.len(), 2);
+    }
+
+    #[test]
+    fn receipt_latency_by_submission_path() {
+        let metrics = FleetMetrics::new();
+        let submitted = |path, receipt_latency_ms| {
+            ActionResult::success(alloy::primitives::TxHash::ZERO).with_submission(Submission {
+                path,
+                receipt_latency_ms,
+            })
+        };
+        let record = |result: &ActionResult| {
+            metrics.record_result("fleet", "fleet.sweep", "wallet_1", result);
+        };
+        for ms in 1..=20 {
+            record(&submitted(SubmissionPath::Realtime, Some(ms)));
+            record(&submitted(SubmissionPath::Standard, Some(ms * 100)));
+        }
+        // Not mined in time: counted, but no latency
+        record(&submitted(SubmissionPath::Standard, None));
+
+        assert_eq!(metrics.submissions_with_path(SubmissionPath::Realtime), 20);
+        assert_eq!(metrics.submissions_with_path(SubmissionPath::Standard), 21);
+        assert_eq!(metrics.p50_receipt_latency_ms(SubmissionPath::Realtime), 11);
+        assert_eq!(metrics.p95_receipt_latency_ms(SubmissionPath::Realtime), 20);
+        assert_eq!(metrics.p50_receipt_latency_ms(SubmissionPath::Standard), 1100);
+        assert_eq!(metrics.snapshot().submissions_by_path[&SubmissionPath::Standard], 21);
+        // Receipt latencies stay out of the action durations
+        assert_eq!(metrics.p50_duration_ms(), 0);
+
+        metrics.reset();
+        assert_eq!(metrics.p95_receipt_latency_ms(SubmissionPath::Realtime), 0);
+    }
+
+    #[test]
+    fn counts_errors_by_class() {
+        let metrics = FleetMetrics::new();
+
+        metrics.record_error(ErrorClass::Transient);
+        metrics.record_error(ErrorClass::Transient);
+        metrics.record_error(ErrorClass::RateLimited);
+
+        assert_eq!(metrics.errors_with_class(ErrorClass::Transient), 2);
+        assert_eq!(metrics.errors_with_class(ErrorClass::RateLimited), 1);
+        assert_eq!(metrics.errors_with_class(ErrorClass::Permanent), 0);
+        assert_eq!(metrics.snapshot().errors_by_class.len(), 2);
+        // Errors are not actions
+        assert_eq!(metrics.total_actions(), 0);
+    }
+
+    #[test]
+    fn compares_gas_used_to_estimates() {
+        let metrics = FleetMetrics::new();
+        let tx_hash = alloy::primitives::TxHash::ZERO;
+        let close = ActionResult::success_with_gas(tx_hash, 90_000).with_gas_estimate(100_000);
+        let over = ActionResult::success_with_gas(tx_hash, 70_000).with_gas_estimate(100_000);
+
+        metrics.record_result("test", "test.action", "wallet_1", &close);
+        metrics.record_result("test", "test.action", "wallet_1", &over);
+        // Neither estimated nor mined actions are left out
+        metrics.record_result("test", "test.action", "wallet_1", &ActionResult::success(tx_hash));
+        let unsent = ActionResult::skipped("reverts").with_gas_estimate(100_000);
+        metrics.record_result("test", "test.action", "wallet_1", &unsent);
+
+        assert!((metrics.avg_gas_used_of_estimate_pct() - 80.0).abs() < 0.01);
+        metrics.reset();
+        assert!(metrics.avg_gas_used_of_estimate_pct().abs() < 0.01);
+    }
+
+    #[test]
+    fn keeps_the_most_recent_samples() {
+        let metrics = FleetMetrics::new();
+
+        // The first 500 fall out of the ring buffer
+        for i in 1..=1500 {
+            metrics.record_action(sample_action(true, i));
+        }
+
+        assert_eq!(metrics.p99_duration_ms(), 1491);
+        assert!((metrics.avg_duration_ms() - 1000.5).abs() < 0.01);
+
+        metrics.reset();
+        assert_eq!(metrics.total_actions(), 0);
+        assert_eq!(metrics.p50_duration_ms(), 0);
+        assert_eq!(metrics.actions_for_wallet("wallet_1"), 0);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn concurrent_recording_adds_up() {
+        const TASKS: u64 = 16;
+        const ACTIONS: u64 = 500;
+
+        let metrics = std::sync::Arc::new(FleetMetrics::new());
+        let tasks: Vec<_> = (0..TASKS)
+            .map(|task| {
+                let metrics = std::sync::Arc::clone(&metrics);
+                tokio::spawn(async move {
+                    for i in 0..ACTIONS {
+                        let mut action = sample_action(i % 4 != 0, i);
+                        action.wallet_id = format!("wallet_{task}");
+                        action.gas_cost_wei = Some(10);
+                        metrics.record_action(action);
+                        if i % 50 == 0 {
+                            // Snapshots while writing must not block or panic
+                            let _ = metrics.snapshot();
+                            tokio::task::yield_now().await;
+                        }
+                    }
+                })
+            })
+            .collect();
+        for task in tasks {
+            task.await.unwrap();
+        }
+
+        let total = TASKS * ACTIONS;
+        assert_eq!(metrics.total_actions(), total);
+        assert_eq!(metrics.successful_actions(), total * 3 / 4);
+        assert_eq!(metrics.failed_actions(), total / 4);
+        assert_eq!(metrics.actions_for_plugin("test"), total);
+        assert_eq!(metrics.total_gas_cost_wei(), u128::from(total) * 10);
+        for task in 0..TASKS {
+            assert_eq!(metrics.actions_for_wallet(&format!("wallet_{task}")), ACTIONS);
+        }
+        assert_eq!(metrics.snapshot().actions_by_wallet.len(), 16);
+    }
+
+    #[test]
+    fn roll_up_sums_fleets_and_scopes_wallets() {
+        let health = PluginHealth::new(HealthSettings::default());
+        let pnl = PnlTotals {
+            data_out: U256::from(100),
+            ..PnlTotals::default()
+        };
+        let snapshot = |fleet: &str, success: bool| {
+            let metrics = FleetMetrics::new();
+            let mut action = sample_action(success, 10);
+            action.gas_cost_wei = Some(7);
+            metrics.record_action(action.clone());
+            metrics.record_action(action);
+            FleetSnapshot {
+                fleet_id: Some(fleet.to_string()),
+                active_wallets: 1,
+                fleet_budget_exhausted: !success,
+                group_stats: vec![GroupStats {
+                    group: "whales".to_string(),
+                    actions_in_window: 1,
+                    max_actions: 4,
+                    window_secs: 60,
+                    utilization: 0.25,
+                }],
+                plugins: vec![health.status("ghostnet", Utc::now())],
+                pnl: PnlSummary {
+                    lifetime: pnl,
+                    by_wallet: HashMap::from([("wallet_1".to_string(), pnl)]),
+                    closed_positions: 1,
+                    ..PnlSummary::default()
+                },
+                degradation: DegradationStatus {
+                    tier: if success {
+                        DegradationTier::ReadOnly
+                    } else {
+                        DegradationTier::Normal
+                    },
+                    ..DegradationStatus::default()
+                },
+                ..metrics.snapshot()
+            }
+        };
+
+        let total = FleetSnapshot::roll_up(&[snapshot("alpha", false), snapshot("beta", true)]);
+        assert_eq!(total.fleet_id, None);
+        assert_eq!(total.active_wallets, 2);
+        assert!(total.fleet_budget_exhausted);
+        assert_eq!(total.total_actions, 4);
+        assert_eq!(total.successful_actions, 2);
+        assert_eq!(total.failed_actions, 2);
+        assert_eq!(total.total_gas_cost_wei, 28);
+        assert_eq!(total.actions_by_plugin["test"], 4);
+        assert_eq!(total.actions_by_status[&ActionStatus::Reverted], 2);
+        // The same wallet id in two fleets is two wallets
+        assert_eq!(total.actions_by_wallet.len(), 2);
+        assert_eq!(total.actions_by_wallet["alpha/wallet_1"], 2);
+        assert_eq!(total.actions_by_wallet["beta/wallet_1"], 2);
+        assert_eq!(total.failures_by_wallet["alpha/wallet_1"], 2);
+        assert_eq!(total.successes_by_wallet["beta/wallet_1"], 2);
+        assert!(!total.successes_by_wallet.contains_key("alpha/wallet_1"));
+        assert_eq!(total.pnl.lifetime.data_out, U256::from(200));
+        assert_eq!(total.pnl.by_wallet["beta/wallet_1"], pnl);
+        assert_eq!(total.pnl.closed_positions, 2);
+        let groups: Vec<_> = total.group_stats.iter().map(|g| g.group.as_str()).collect();
+        assert_eq!(groups, ["alpha/whales", "beta/whales"]);
+        let plugins: Vec<_> = total.plugins.iter().map(|p| p.plugin_id.as_str()).collect();
+        assert_eq!(plugins, ["alpha/ghostnet", "beta/ghostnet"]);
+        assert_eq!(total.actions_by_hour.iter().sum::<u64>(), 4);
+        assert_eq!(total.degradation.tier, DegradationTier::ReadOnly);
+    }
+
+    #[test]
+    fn flags_actions_in_lockstep() {
+        use crate::clock::VirtualClock;
+        use chrono::Duration;
+
+        let start = Utc.with_ymd_and_hms(2025, 1, 1, 9, 0, 0).unwrap();
+        let clock = std::sync::Arc::new(VirtualClock::new(start));
+        let metrics = FleetMetrics::new()
+            .with_clock(std::sync::Arc::clone(&clock) as _)
+            .with_correlation(CorrelationSettings {
+                min_actions: 10,
+                ..CorrelationSettings::default()
+            });
+
+        // One action every 15 minutes for 5 hours
+        for _ in 0..20 {
+            metrics.record_action(sample_action(true, 10));
+            clock.advance(Duration::minutes(15));
+        }
+        let spread = metrics.correlation();
+        assert_eq!(spread.actions, 20);
+        assert!(!spread.flagged);
+        assert_eq!(metrics.actions_by_hour()[9..14], [4; 5]);
+
+        // Then the whole fleet within a minute; skipped decisions do not count
+        for _ in 0..20 {
+            metrics.record_action(sample_action(false, 10));
+            let mut skipped = sample_action(true, 10);
+            skipped.status = ActionStatus::Skipped;
+            metrics.record_action(skipped);
+            clock.advance(Duration::seconds(3));
+        }
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot.correlation.actions, 40);
+        assert!((snapshot.correlation.concentration - 0.5).abs() < 1e-9);
+        assert!(snapshot.correlation.flagged);
+        assert_eq!(snapshot.actions_by_hour[14], 20);
+
+        metrics.reset();
+        assert_eq!(metrics.correlation(), ActivityCorrelation::default());
+        assert_eq!(metrics.actions_by_hour(), [0; 24]);
+    }
 }
@@ -0,0 +1,130 @@
+//! Profit and loss totals of wallets and fleets.
+//!
+//! The service keeps the ledger these are taken from; this module only
+//! defines the totals that go into a [`FleetSnapshot`](super::FleetSnapshot).
+//! DATA and native gas are kept apart: converting one into the other takes
+//! a price the fleet does not have.
+
+use std::collections::HashMap;
+
+use alloy::primitives::{I256, U256};
+use serde::{Deserialize, Serialize};
+
+/// DATA a wallet or fleet paid and received, and the gas it paid, in wei.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PnlTotals {
+    /// DATA paid out: stakes and wagers.
+    pub data_out: U256,
+
+    /// DATA received: what exits paid out, winnings, refunds and claimed
+    /// rewards.
+    pub data_in: U256,
+
+    /// Native gas paid.
+    pub gas_wei: U256,
+}
+
+impl PnlTotals {
+    /// DATA received less DATA paid out.
+    ///
+    /// Stakes still open count as paid out, so this only turns positive
+    /// once more came back than was put in.
+    #[must_use]
+    pub fn net_data(&self) -> I256 {
+        signed(self.data_in).saturating_sub(signed(self.data_out))
+    }
+
+    /// Add `other` to these totals.
+    pub const fn add(&mut self, other: &Self) {
+        self.data_out = self.data_out.saturating_add(other.data_out);
+        self.data_in = self.data_in.saturating_add(other.data_in);
+        self.gas_wei = self.gas_wei.saturating_add(other.gas_wei);
+    }
+
+    /// What was added to `start` to reach `self`.
+    #[must_use]
+    pub const fn since(&self, start: &Self) -> Self {
+        Self {
+            data_out: self.data_out.saturating_sub(start.data_out),
+            data_in: self.data_in.saturating_sub(start.data_in),
+            gas_wei: self.gas_wei.saturating_sub(start.gas_wei),
+        }
+    }
+}
+
+/// Profit and loss of a fleet, as of a snapshot.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PnlSummary {
+    /// Totals since the ledger was started.
+    pub lifetime: PnlTotals,
+
+    /// Totals of the current UTC day.
+    pub today: PnlTotals,
+
+    /// Lifetime totals by wallet.
+    pub by_wallet: HashMap<String, PnlTotals>,
+
+    /// DATA paid out for closed positions less what was staked in them.
+    pub realized_data: I256,
+
+    /// Positions closed, by an exit or by being lost.
+    pub closed_positions: u64,
+}
+
+impl PnlSummary {
+    /// Add `other` to this summary, with its wallets named by `scoped`.
+    pub(crate) fn add(&mut self, other: &Self, scoped: impl Fn(&str) -> String) {
+        self.lifetime.add(&other.lifetime);
+        self.today.add(&other.today);
+        for (wallet, totals) in &other.by_wallet {
+            self.by_wallet
+                .entry(scoped(wallet))
+                .or_default()
+                .add(totals);
+        }
+        self.realized_data = self.realized_data.saturating_add(other.realized_data);
+        self.closed_positions += other.closed_positions;
+    }
+}
+
+/// `amount` as a signed amount, capped at [`I256::MAX`].
+fn signed(amount: U256) -> I256 {
+    I256::try_from(amount).unwrap_or(I256::MAX)
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn totals(data_out: u64, data_in: u64, gas_wei: u64) -> PnlTotals {
+        PnlTotals {
+            data_out: U256::from(data_out),
+            data_in: U256::from(data_in),
+            gas_wei: U256::from(gas_wei),
+        }
+    }
+
+    #[test]
+    fn net_data_is_signed() {
+        assert_eq!(totals(100, 40, 5).net_data(), I256::try_from(-60i64).unwrap());
+        assert_eq!(totals(100, 140, 5).net_data(), I256::try_from(40i64).unwrap());
+        let huge = PnlTotals {
+            data_in: U256::MAX,
+            ..PnlTotals::default()
+        };
+        assert_eq!(huge.net_data(), I256::MAX);
+    }
+
+    #[test]
+    fn totals_add_and_diff() {
+        let mut sum = totals(100, 40, 5);
+        sum.add(&totals(10, 20, 1));
+        assert_eq!(sum, totals(110, 60, 6));
+        assert_eq!(sum.since(&totals(100, 40, 5)), totals(10, 20, 1));
+    }
+}
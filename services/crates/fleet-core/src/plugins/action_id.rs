@@ -0,0 +1,310 @@
+//! Action identifiers.
+//!
+//! Every action is named `<plugin>.<action>`, e.g. `ghostnet.jack_in`: the ID
+//! of the plugin that owns it, a dot, and the action's own name. Both parts
+//! are non-empty and made of `a-z`, `0-9` and `_` only, so an ID that is
+//! misspelled in a profile or a metrics query fails to parse rather than
+//! silently matching nothing.
+//!
+//! Plugins name their actions with [`action_id!`](crate::action_id) or
+//! [`checked_action_id`], which reject a malformed ID at compile time, and
+//! the [`PluginRegistry`](super::PluginRegistry) refuses plugins listing an
+//! action that is malformed or not their own.
+
+use std::borrow::Borrow;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Separator of the plugin and action parts.
+const SEPARATOR: char = '.';
+
+/// Wildcard ending an [action pattern](ActionId::matches_pattern).
+const WILDCARD: char = '*';
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// ACTION ID
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Unique identifier for an action type, `<plugin>.<action>`.
+///
+/// [`new`](Self::new) takes any string, as IDs read back from state files
+/// and configs do; [`parse`](Self::parse) checks the format.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ActionId(pub String);
+
+impl ActionId {
+    /// Create a new action ID.
+    #[must_use]
+    pub fn new(id: impl Into<String>) -> Self {
+        Self(id.into())
+    }
+
+    /// Parse an action ID, checking it is of the `<plugin>.<action>` format.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`ActionIdError`] saying what is wrong with `id`.
+    pub fn parse(id: impl Into<String>) -> Result<Self, ActionIdError> {
+        let id = id.into();
+        match flaw(&id) {
+            None => Ok(Self(id)),
+            Some(Flaw::Shape) => Err(ActionIdError::Shape(id)),
+            Some(Flaw::EmptySegment) => Err(ActionIdError::EmptySegment(id)),
+            Some(Flaw::Character(at)) => {
+                let found = id[at..].chars().next().unwrap_or_default();
+                Err(ActionIdError::Character { id, found })
+            }
+        }
+    }
+
+    /// Get the action ID as a string slice.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// ID of the plugin owning the action: everything before the first dot,
+    /// or the whole ID if it has none.
+    #[must_use]
+    pub fn plugin_part(&self) -> &str {
+        self.0.split_once(SEPARATOR).map_or(&self.0, |(plugin, _)| plugin)
+    }
+
+    /// Name of the action within its plugin: everything after the first
+    /// dot, empty if the ID has none.
+    #[must_use]
+    pub fn action_part(&self) -> &str {
+        self.0.split_once(SEPARATOR).map_or("", |(_, action)| action)
+    }
+
+    /// Check whether the ID matches `pattern`.
+    ///
+    /// A pattern is an action ID, matched exactly, or a prefix followed by
+    /// a single trailing `*`, matching every ID that starts with it:
+    /// `ghostnet.*` matches all GHOSTNET actions and `*` every action. A `*`
+    /// anywhere else is not a wildcard, so such patterns match nothing.
+    #[must_use]
+    pub fn matches_pattern(&self, pattern: &str) -> bool {
+        match pattern.strip_suffix(WILDCARD) {
+            Some(prefix) if !prefix.contains(WILDCARD) => self.0.starts_with(prefix),
+            Some(_) => false,
+            None => self.0 == pattern,
+        }
+    }
+}
+
+impl std::fmt::Display for ActionId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Borrow<str> for ActionId {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&str> for ActionId {
+    fn from(s: &str) -> Self {
+        Self(s.to_string())
+    }
+}
+
+impl From<String> for ActionId {
+    fn from(s: String) -> Self {
+        Self(s)
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// VALIDATION
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Why a string is not a valid [`ActionId`].
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ActionIdError {
+    /// Not a plugin part and an action part joined by one dot.
+    #[error("action ID {0:?} is not of the form <plugin>.<action>")]
+    Shape(String),
+
+    /// The plugin or the action part is empty.
+    #[error("action ID {0:?} has an empty plugin or action part")]
+    EmptySegment(String),
+
+    /// A character other than `a-z`, `0-9`, `_` and the dot.
+    #[error("action ID {id:?} contains {found:?}; only a-z, 0-9 and _ are allowed")]
+    Character {
+        /// The ID.
+        id: String,
+        /// First character not allowed.
+        found: char,
+    },
+}
+
+/// What [`flaw`] found wrong with an ID.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Flaw {
+    Shape,
+    EmptySegment,
+    /// Byte offset of the first character not allowed.
+    Character(usize),
+}
+
+/// First flaw of `id` as an action ID, if any. Usable in const contexts.
+const fn flaw(id: &str) -> Option<Flaw> {
+    let bytes = id.as_bytes();
+    let mut dots = 0;
+    let mut segment = 0;
+    let mut at = 0;
+    while at < bytes.len() {
+        let byte = bytes[at];
+        if byte == b'.' {
+            if segment == 0 {
+                return Some(Flaw::EmptySegment);
+            }
+            dots += 1;
+            segment = 0;
+        } else if byte.is_ascii_lowercase() || byte.is_ascii_digit() || byte == b'_' {
+            segment += 1;
+        } else {
+            return Some(Flaw::Character(at));
+        }
+        at += 1;
+    }
+    if dots != 1 {
+        Some(Flaw::Shape)
+    } else if segment == 0 {
+        Some(Flaw::EmptySegment)
+    } else {
+        None
+    }
+}
+
+/// Check that `id` is of the `<plugin>.<action>` format.
+#[must_use]
+pub const fn is_valid_action_id(id: &str) -> bool {
+    flaw(id).is_none()
+}
+
+/// Return `id`, panicking if it is not a valid action ID.
+///
+/// Meant for constants, where the panic fails the build:
+///
+/// ```
+/// use fleet_core::plugins::checked_action_id;
+///
+/// pub const ACTION_STAKE: &str = checked_action_id("my_plugin.stake");
+/// ```
+///
+/// # Panics
+///
+/// Panics if `id` is not of the `<plugin>.<action>` format.
+#[must_use]
+#[allow(clippy::panic)] // Evaluated in const context, so a panic is a build error
+pub const fn checked_action_id(id: &'static str) -> &'static str {
+    match flaw(id) {
+        None => id,
+        Some(Flaw::Shape) => panic!("action ID is not of the form <plugin>.<action>"),
+        Some(Flaw::EmptySegment) => panic!("action ID has an empty plugin or action part"),
+        Some(Flaw::Character(_)) => panic!("action ID may only contain a-z, 0-9, _ and one dot"),
+    }
+}
+
+/// Create an [`ActionId`](crate::plugins::ActionId) from a literal, checked
+/// at compile time.
+///
+/// ```
+/// use fleet_core::action_id;
+///
+/// let stake = action_id!("my_plugin.stake");
+/// assert_eq!(stake.plugin_part(), "my_plugin");
+/// ```
+///
+/// A malformed literal, such as `action_id!("my_plugin")`, fails the build.
+#[macro_export]
+macro_rules! action_id {
+    ($id:literal) => {{
+        const ID: &str = $crate::plugins::checked_action_id($id);
+        $crate::plugins::ActionId::new(ID)
+    }};
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_accepts_plugin_and_action() {
+        let id = ActionId::parse("ghostnet.jack_in").unwrap();
+        assert_eq!(id.plugin_part(), "ghostnet");
+        assert_eq!(id.action_part(), "jack_in");
+        assert_eq!(ActionId::parse("v2.step_3").unwrap().as_str(), "v2.step_3");
+        assert_eq!(action_id!("ghostnet.jack_in"), id);
+    }
+
+    #[test]
+    fn parse_rejects_malformed_ids() {
+        let shape = |id: &str| ActionIdError::Shape(id.to_string());
+        let empty = |id: &str| ActionIdError::EmptySegment(id.to_string());
+        let character = |id: &str, found| ActionIdError::Character {
+            id: id.to_string(),
+            found,
+        };
+
+        assert_eq!(ActionId::parse("ghostnet").unwrap_err(), shape("ghostnet"));
+        assert_eq!(ActionId::parse("").unwrap_err(), shape(""));
+        assert_eq!(ActionId::parse("a.b.c").unwrap_err(), shape("a.b.c"));
+        assert_eq!(ActionId::parse(".jack_in").unwrap_err(), empty(".jack_in"));
+        assert_eq!(ActionId::parse("ghostnet.").unwrap_err(), empty("ghostnet."));
+        assert_eq!(ActionId::parse("a..b").unwrap_err(), empty("a..b"));
+        assert_eq!(
+            ActionId::parse("ghostnet.Jack_in").unwrap_err(),
+            character("ghostnet.Jack_in", 'J')
+        );
+        assert_eq!(
+            ActionId::parse("ghostnet.jack-in").unwrap_err(),
+            character("ghostnet.jack-in", '-')
+        );
+        assert_eq!(ActionId::parse("café.x").unwrap_err(), character("café.x", 'é'));
+        assert!(!is_valid_action_id("ghostnet.jack in"));
+    }
+
+    #[test]
+    fn parts_of_unchecked_ids() {
+        let bare = ActionId::new("jack_in");
+        assert_eq!(bare.plugin_part(), "jack_in");
+        assert_eq!(bare.action_part(), "");
+        assert_eq!(ActionId::new("a.b.c").action_part(), "b.c");
+    }
+
+    #[test]
+    fn patterns_match_exactly_or_by_prefix() {
+        let id = action_id!("ghostnet.jack_in");
+        assert!(id.matches_pattern("ghostnet.jack_in"));
+        assert!(id.matches_pattern("ghostnet.*"));
+        assert!(id.matches_pattern("ghostnet.jack*"));
+        assert!(id.matches_pattern("*"));
+        assert!(!id.matches_pattern("ghostnet.jack"));
+        assert!(!id.matches_pattern("arcade.*"));
+
+        // Only a trailing `*` is a wildcard
+        assert!(!id.matches_pattern("*.jack_in"));
+        assert!(!id.matches_pattern("ghost*.jack_in"));
+        assert!(!id.matches_pattern("ghost*.*"));
+    }
+
+    #[test]
+    #[should_panic(expected = "<plugin>.<action>")]
+    fn checked_ids_panic_outside_const_contexts() {
+        let id = std::hint::black_box("ghostnet");
+        let _ = checked_action_id(id);
+    }
+}
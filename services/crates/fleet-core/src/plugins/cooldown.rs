@@ -0,0 +1,220 @@
+//! Per-action cooldowns.
+//!
+//! Even with scheduler jitter a wallet could pick the same action on every
+//! tick, which looks robotic and concentrates risk. Each action can therefore
+//! have a cooldown: a minimum time between two executions by the same wallet.
+//!
+//! Cooldowns are resolved from two sources, highest precedence first:
+//!
+//! 1. The profile's [`action_cooldown_secs`](crate::profiles::BehaviorProfile::action_cooldown_secs)
+//! 2. The plugin's [`default_cooldown`](super::ActionPlugin::default_cooldown)
+//!
+//! and checked against the wallet's
+//! [`last_executed`](crate::wallet::WalletState::last_executed) timestamps.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Utc};
+
+use super::{ActionId, ActionPlugin};
+use crate::profiles::BehaviorProfile;
+use crate::wallet::WalletState;
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// ACTION COOLDOWNS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Resolved cooldowns and last executions of one wallet.
+///
+/// # Example
+///
+/// ```
+/// use chrono::{Duration, Utc};
+/// use fleet_core::plugins::{ActionCooldowns, ActionId};
+///
+/// let now = Utc::now();
+/// let cooldowns = ActionCooldowns::new()
+///     .with_cooldown("ghostnet.hashcrash_bet", Duration::minutes(10))
+///     .with_last_executed("ghostnet.hashcrash_bet", now - Duration::minutes(4));
+///
+/// assert!(cooldowns.is_on_cooldown("ghostnet.hashcrash_bet", now));
+/// assert_eq!(cooldowns.remaining("ghostnet.hashcrash_bet", now), Some(Duration::minutes(6)));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ActionCooldowns {
+    /// Cooldown per action; actions without an entry have none.
+    cooldowns: HashMap<ActionId, Duration>,
+
+    /// When each action was last executed.
+    last_executed: HashMap<ActionId, DateTime<Utc>>,
+}
+
+impl ActionCooldowns {
+    /// Create an empty set of cooldowns.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Resolve the cooldowns of every action the plugins offer for a wallet.
+    #[must_use]
+    pub fn resolve(
+        plugins: &[Arc<dyn ActionPlugin>],
+        profile: &BehaviorProfile,
+        wallet: &WalletState,
+    ) -> Self {
+        let cooldowns = plugins
+            .iter()
+            .flat_map(|plugin| {
+                plugin.available_actions().into_iter().filter_map(|action| {
+                    profile
+                        .cooldown_for(action.as_str(), plugin.default_cooldown(&action))
+                        .map(|cooldown| (action, cooldown))
+                })
+            })
+            .collect();
+
+        Self {
+            cooldowns,
+            last_executed: wallet.last_executed.clone(),
+        }
+    }
+
+    /// Set the cooldown of an action.
+    #[must_use]
+    pub fn with_cooldown(mut self, action: impl Into<ActionId>, cooldown: Duration) -> Self {
+        self.cooldowns.insert(action.into(), cooldown);
+        self
+    }
+
+    /// Set when an action was last executed.
+    #[must_use]
+    pub fn with_last_executed(mut self, action: impl Into<ActionId>, at: DateTime<Utc>) -> Self {
+        self.last_executed.insert(action.into(), at);
+        self
+    }
+
+    /// Cooldown of an action, if it has one.
+    #[must_use]
+    pub fn cooldown(&self, action: &str) -> Option<Duration> {
+        self.cooldowns.get(action).copied()
+    }
+
+    /// Time left until an action may be executed again at `now`.
+    ///
+    /// `None` if the action is not on cooldown.
+    #[must_use]
+    pub fn remaining(&self, action: &str, now: DateTime<Utc>) -> Option<Duration> {
+        let available_at = *self.last_executed.get(action)? + self.cooldown(action)?;
+        (available_at > now).then(|| available_at - now)
+    }
+
+    /// Check whether an action is on cooldown at `now`.
+    #[must_use]
+    pub fn is_on_cooldown(&self, action: &str, now: DateTime<Utc>) -> bool {
+        self.remaining(action, now).is_some()
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::Address;
+    use async_trait::async_trait;
+
+    use super::*;
+    use crate::error::Result;
+    use crate::plugins::{Action, ActionResult, PluginContext};
+
+    /// Plugin with a 10 minute default cooldown on `mock.bet`.
+    #[derive(Debug)]
+    struct MockPlugin;
+
+    #[async_trait]
+    impl ActionPlugin for MockPlugin {
+        #[allow(clippy::unnecessary_literal_bound)] // Trait signature defines `&str`
+        fn id(&self) -> &str {
+            "mock"
+        }
+
+        #[allow(clippy::unnecessary_literal_bound)] // Trait signature defines `&str`
+        fn name(&self) -> &str {
+            "Mock"
+        }
+
+        fn available_actions(&self) -> Vec<ActionId> {
+            vec![ActionId::new("mock.bet"), ActionId::new("mock.stake")]
+        }
+
+        fn default_cooldown(&self, action: &ActionId) -> Option<Duration> {
+            (action.as_str() == "mock.bet").then(|| Duration::minutes(10))
+        }
+
+        async fn decide_action(
+            &self,
+            _wallet: &WalletState,
+            _profile: &BehaviorProfile,
+            _context: &mut PluginContext<'_>,
+        ) -> Result<Option<Action>> {
+            Ok(None)
+        }
+
+        async fn execute_action(
+            &self,
+            _action: &Action,
+            _wallet: &WalletState,
+            _nonce: u64,
+        ) -> Result<ActionResult> {
+            Ok(ActionResult::failure("mock"))
+        }
+
+        async fn read_state(&self, _address: Address) -> Result<serde_json::Value> {
+            Ok(serde_json::Value::Null)
+        }
+    }
+
+    #[test]
+    fn cooldown_expires() {
+        let now = Utc::now();
+        let cooldowns = ActionCooldowns::new()
+            .with_cooldown("mock.bet", Duration::minutes(10))
+            .with_last_executed("mock.bet", now - Duration::minutes(10));
+
+        assert!(cooldowns.is_on_cooldown("mock.bet", now - Duration::seconds(1)));
+        assert!(!cooldowns.is_on_cooldown("mock.bet", now));
+        // Never executed, or no cooldown configured
+        assert!(!cooldowns.is_on_cooldown("mock.stake", now));
+        assert!(
+            !ActionCooldowns::new()
+                .with_last_executed("mock.bet", now)
+                .is_on_cooldown("mock.bet", now)
+        );
+    }
+
+    #[test]
+    fn resolve_prefers_profile_overrides() {
+        let plugins: Vec<Arc<dyn ActionPlugin>> = vec![Arc::new(MockPlugin)];
+        let mut wallet = WalletState::new("test".into(), Address::ZERO);
+        let now = Utc::now();
+        wallet.record_execution("mock.bet".into(), now);
+
+        let cooldowns = ActionCooldowns::resolve(&plugins, &BehaviorProfile::new("p"), &wallet);
+        assert_eq!(cooldowns.cooldown("mock.bet"), Some(Duration::minutes(10)));
+        assert_eq!(cooldowns.cooldown("mock.stake"), None);
+        assert!(cooldowns.is_on_cooldown("mock.bet", now + Duration::minutes(5)));
+
+        let mut profile = BehaviorProfile::new("p");
+        profile.action_cooldown_secs.insert("mock.bet".into(), 60);
+        profile
+            .action_cooldown_secs
+            .insert("mock.stake".into(), 300);
+        let cooldowns = ActionCooldowns::resolve(&plugins, &profile, &wallet);
+        assert_eq!(cooldowns.cooldown("mock.bet"), Some(Duration::minutes(1)));
+        assert_eq!(cooldowns.cooldown("mock.stake"), Some(Duration::minutes(5)));
+        assert!(!cooldowns.is_on_cooldown("mock.bet", now + Duration::minutes(5)));
+    }
+}
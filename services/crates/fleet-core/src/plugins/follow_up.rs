@@ -0,0 +1,313 @@
+//! Follow-up actions.
+//!
+//! Some flows take several actions in a row: approve a token, then stake it;
+//! extract, then re-enter at another level. Deciding each step on its own
+//! would leave hours between them, so a decided [`Action`] can carry the
+//! [`FollowUpAction`]s that come after it. Once its result is recorded, each
+//! follow-up whose [`FollowUpCondition`] matches the outcome is queued on the
+//! wallet as a [`PendingFollowUp`], due after a random delay from its range,
+//! and runs in place of the wallet's next decision once it is due.
+//!
+//! A follow-up can carry follow-ups of its own, up to [`MAX_CHAIN_DEPTH`]
+//! actions after the decided one; deeper ones are dropped, so a plugin cannot
+//! chain a wallet into a loop.
+//!
+//! ```
+//! use fleet_core::plugins::{Action, FollowUpAction, FollowUpCondition};
+//!
+//! let approve = Action::new("ghostnet.approve", "Approve").with_follow_up(
+//!     FollowUpAction::new("ghostnet.jack_in", "Jack In")
+//!         .with_delay_secs(30, 120)
+//!         .with_condition(FollowUpCondition::OnSuccess),
+//! );
+//!
+//! let stake = approve.follow_up[0].to_action(1);
+//! assert_eq!(stake.id.as_str(), "ghostnet.jack_in");
+//! assert_eq!(stake.chain_depth, 1);
+//! ```
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{Action, ActionId, ActionStatus};
+
+/// Most actions a chain may run after the action that was decided.
+pub const MAX_CHAIN_DEPTH: u32 = 3;
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// FOLLOW-UP ACTIONS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Outcomes of the preceding action a follow-up runs after.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FollowUpCondition {
+    /// The action succeeded, or was simulated in a dry run.
+    #[default]
+    OnSuccess,
+
+    /// The action reverted or was dropped.
+    OnFailure,
+
+    /// The action was attempted, whatever its outcome.
+    Always,
+}
+
+impl FollowUpCondition {
+    /// Check whether a follow-up runs after an action with `status`.
+    ///
+    /// Skipped actions never attempted anything, so nothing follows them.
+    #[must_use]
+    pub const fn matches(self, status: ActionStatus) -> bool {
+        let succeeded = matches!(status, ActionStatus::Succeeded | ActionStatus::Simulated);
+        match self {
+            Self::OnSuccess => succeeded,
+            Self::OnFailure => status.is_failure(),
+            Self::Always => succeeded || status.is_failure(),
+        }
+    }
+}
+
+/// An action to run after another one, see the [module docs](self).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FollowUpAction {
+    /// Action ID, of the plugin that decided the preceding action.
+    pub action: ActionId,
+
+    /// Human-readable action name.
+    pub name: String,
+
+    /// Action data, as the plugin would have decided it.
+    #[serde(default)]
+    pub data: serde_json::Value,
+
+    /// Shortest delay after the preceding action's result (seconds).
+    #[serde(default)]
+    pub min_delay_secs: u64,
+
+    /// Longest delay after the preceding action's result (seconds).
+    #[serde(default)]
+    pub max_delay_secs: u64,
+
+    /// Outcomes of the preceding action this one runs after.
+    #[serde(default)]
+    pub condition: FollowUpCondition,
+
+    /// Follow-ups of this action.
+    #[serde(default)]
+    pub follow_up: Vec<Self>,
+}
+
+impl FollowUpAction {
+    /// A follow-up running right after the preceding action succeeded.
+    #[must_use]
+    pub fn new(action: impl Into<ActionId>, name: impl Into<String>) -> Self {
+        Self {
+            action: action.into(),
+            name: name.into(),
+            data: serde_json::Value::Null,
+            min_delay_secs: 0,
+            max_delay_secs: 0,
+            condition: FollowUpCondition::OnSuccess,
+            follow_up: Vec::new(),
+        }
+    }
+
+    /// The follow-up of a decided action, running after it with `condition`.
+    #[must_use]
+    pub fn of(action: Action, condition: FollowUpCondition) -> Self {
+        Self {
+            action: action.id,
+            name: action.name,
+            data: action.data,
+            min_delay_secs: 0,
+            max_delay_secs: 0,
+            condition,
+            follow_up: action.follow_up,
+        }
+    }
+
+    /// Set the action data.
+    #[must_use]
+    pub fn with_data(mut self, data: serde_json::Value) -> Self {
+        self.data = data;
+        self
+    }
+
+    /// Wait between `min` and `max` seconds after the preceding action.
+    #[must_use]
+    pub fn with_delay_secs(mut self, min: u64, max: u64) -> Self {
+        self.min_delay_secs = min.min(max);
+        self.max_delay_secs = max.max(min);
+        self
+    }
+
+    /// Set the outcomes the follow-up runs after.
+    #[must_use]
+    pub const fn with_condition(mut self, condition: FollowUpCondition) -> Self {
+        self.condition = condition;
+        self
+    }
+
+    /// Add a follow-up of this action.
+    #[must_use]
+    pub fn with_follow_up(mut self, follow_up: Self) -> Self {
+        self.follow_up.push(follow_up);
+        self
+    }
+
+    /// The action to run, `depth` actions after the decided one.
+    #[must_use]
+    pub fn to_action(&self, depth: u32) -> Action {
+        let mut action =
+            Action::with_data(self.action.clone(), self.name.clone(), self.data.clone());
+        action.follow_up.clone_from(&self.follow_up);
+        action.chain_depth = depth;
+        action
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// PENDING FOLLOW-UPS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// A follow-up queued on a wallet, waiting to become due.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingFollowUp {
+    /// Plugin that decided the chain.
+    pub plugin_id: String,
+
+    /// The follow-up to run.
+    pub follow_up: FollowUpAction,
+
+    /// When it may run.
+    pub due: DateTime<Utc>,
+
+    /// Actions between the decided one and this one, counting this one.
+    pub depth: u32,
+}
+
+impl PendingFollowUp {
+    /// Queue the follow-ups of `action` that run after `status`, due at the
+    /// time `due_at` picks for each.
+    ///
+    /// Returns nothing once the chain is [`MAX_CHAIN_DEPTH`] deep.
+    pub fn after(
+        plugin_id: &str,
+        action: &Action,
+        status: ActionStatus,
+        mut due_at: impl FnMut(&FollowUpAction) -> DateTime<Utc>,
+    ) -> Vec<Self> {
+        let depth = action.chain_depth + 1;
+        if depth > MAX_CHAIN_DEPTH {
+            return Vec::new();
+        }
+        action
+            .follow_up
+            .iter()
+            .filter(|follow_up| follow_up.condition.matches(status))
+            .map(|follow_up| Self {
+                plugin_id: plugin_id.to_string(),
+                follow_up: follow_up.clone(),
+                due: due_at(follow_up),
+                depth,
+            })
+            .collect()
+    }
+
+    /// Check whether the follow-up may run at `now`.
+    #[must_use]
+    pub fn is_due_at(&self, now: DateTime<Utc>) -> bool {
+        self.due <= now
+    }
+
+    /// The action to run.
+    #[must_use]
+    pub fn action(&self) -> Action {
+        self.follow_up.to_action(self.depth)
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use chrono::Duration;
+
+    use super::*;
+
+    #[test]
+    fn conditions_filter_by_outcome() {
+        let now = Utc::now();
+        let action = Action::new("p.first", "First")
+            .with_follow_up(FollowUpAction::new("p.success", "Success"))
+            .with_follow_up(
+                FollowUpAction::new("p.failure", "Failure")
+                    .with_condition(FollowUpCondition::OnFailure),
+            )
+            .with_follow_up(
+                FollowUpAction::new("p.always", "Always").with_condition(FollowUpCondition::Always),
+            );
+
+        let queued = |status| -> Vec<String> {
+            PendingFollowUp::after("p", &action, status, |_| now)
+                .into_iter()
+                .map(|pending| pending.follow_up.action.to_string())
+                .collect()
+        };
+        assert_eq!(queued(ActionStatus::Succeeded), ["p.success", "p.always"]);
+        assert_eq!(queued(ActionStatus::Simulated), ["p.success", "p.always"]);
+        assert_eq!(queued(ActionStatus::Reverted), ["p.failure", "p.always"]);
+        assert_eq!(queued(ActionStatus::Dropped), ["p.failure", "p.always"]);
+        assert!(queued(ActionStatus::Skipped).is_empty());
+    }
+
+    #[test]
+    fn chains_stop_at_the_depth_cap() {
+        let now = Utc::now();
+        let looping = FollowUpAction::new("p.again", "Again");
+        let mut action = Action::new("p.again", "Again").with_follow_up(looping.clone());
+
+        let mut depths = Vec::new();
+        let queued = |action: &Action| {
+            PendingFollowUp::after("p", action, ActionStatus::Succeeded, |_| now).pop()
+        };
+        while let Some(pending) = queued(&action) {
+            depths.push(pending.depth);
+            // Every step would follow itself up once more
+            action = pending.action().with_follow_up(looping.clone());
+        }
+        assert_eq!(depths, (1..=MAX_CHAIN_DEPTH).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn pending_follow_ups_roundtrip() {
+        let due = Utc::now() + Duration::minutes(2);
+        let action = Action::new("p.approve", "Approve").with_follow_up(
+            FollowUpAction::new("p.stake", "Stake")
+                .with_data(serde_json::json!({ "amount": "100" }))
+                .with_delay_secs(120, 60)
+                .with_follow_up(FollowUpAction::new("p.boost", "Boost")),
+        );
+        let pending = PendingFollowUp::after("p", &action, ActionStatus::Succeeded, |_| due)
+            .pop()
+            .unwrap();
+        assert_eq!(
+            (pending.follow_up.min_delay_secs, pending.follow_up.max_delay_secs),
+            (60, 120)
+        );
+        assert!(!pending.is_due_at(due - Duration::seconds(1)));
+        assert!(pending.is_due_at(due));
+
+        let json = serde_json::to_string(&pending).unwrap();
+        let restored: PendingFollowUp = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, pending);
+
+        let stake = restored.action();
+        assert_eq!(stake.data["amount"], "100");
+        assert_eq!((stake.chain_depth, stake.follow_up.len()), (1, 1));
+    }
+}
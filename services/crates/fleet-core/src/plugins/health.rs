@@ -0,0 +1,568 @@
+//! Health of plugins, and switching off those that keep failing.
+//!
+//! [`PluginHealth`] counts the decisions and executions of each plugin over a
+//! sliding window. A plugin whose decisions or executions fail more often
+//! than `max_error_rate`, over at least `min_calls` of them, is disabled: its
+//! actions are no longer considered, so wallets fall through to other
+//! plugins or skip the cycle. Every `probe_interval`, a single wallet asks a
+//! disabled plugin again; if it decides, and executes the action if that is
+//! chosen, without an error, the plugin is enabled again.
+//!
+//! An operator can force a plugin on or off with a [`PluginOverride`], which
+//! beats the automatic state until it is cleared.
+//!
+//! Rate-limited errors are the endpoint's doing rather than the plugin's, and
+//! are not counted.
+//!
+//! ```text
+//! ┌─────────┐   error rate   ┌──────────┐ probe interval ┌─────────┐
+//! │ Enabled │ ──────────────▶│ Disabled │ ──────────────▶│ Probing │
+//! └─────────┘  > threshold   └──────────┘                └─────────┘
+//!      ▲                           ▲       probe fails        │
+//!      │                           └──────────────────────────┤
+//!      └──────────────────────────────────────────────────────┘
+//!                           probe succeeds
+//! ```
+
+use std::collections::{HashMap, VecDeque};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use super::registry::PluginId;
+use crate::error::ErrorClass;
+
+/// When plugins are disabled and probed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HealthSettings {
+    /// Disable plugins that fail too often. Without it, plugins are only
+    /// disabled by an operator.
+    pub auto_disable: bool,
+
+    /// Window over which calls are counted.
+    pub window: Duration,
+
+    /// Calls of a kind within the window before their error rate counts.
+    pub min_calls: u32,
+
+    /// Share of failed calls of a kind within the window above which the
+    /// plugin is disabled (0.0 - 1.0).
+    pub max_error_rate: f64,
+
+    /// Time between probes of a disabled plugin.
+    pub probe_interval: Duration,
+}
+
+impl Default for HealthSettings {
+    fn default() -> Self {
+        Self {
+            auto_disable: true,
+            window: Duration::from_secs(600),
+            min_calls: 10,
+            max_error_rate: 0.5,
+            probe_interval: Duration::from_secs(300),
+        }
+    }
+}
+
+/// A kind of plugin call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PluginCall {
+    /// [`ActionPlugin::decide_action`](super::ActionPlugin::decide_action).
+    Decide,
+
+    /// [`ActionPlugin::execute_action`](super::ActionPlugin::execute_action).
+    Execute,
+}
+
+/// An operator's choice that beats a plugin's automatic state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PluginOverride {
+    /// Keep the plugin enabled, however often it fails.
+    Enabled,
+
+    /// Keep the plugin disabled, without probes.
+    Disabled,
+}
+
+/// State of a plugin, overrides included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PluginState {
+    /// Considered for every wallet.
+    Enabled,
+
+    /// Not considered.
+    Disabled,
+
+    /// Disabled, with a probe in flight.
+    Probing,
+}
+
+/// Whether a plugin is asked to decide for a wallet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Admission {
+    /// Ask it as usual.
+    Enabled,
+
+    /// Ask it, as the probe of a disabled plugin. The outcome must be
+    /// recorded, or the probe [cancelled](PluginHealth::cancel_probe).
+    Probe,
+
+    /// Leave it out.
+    Disabled,
+}
+
+/// Calls of one kind within the window.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CallCounts {
+    /// Calls made.
+    pub calls: u32,
+
+    /// Calls that failed.
+    pub errors: u32,
+}
+
+/// Health of one plugin, as reported in the
+/// [`FleetSnapshot`](crate::metrics::FleetSnapshot).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PluginHealthStatus {
+    /// Plugin ID.
+    pub plugin_id: PluginId,
+
+    /// Current state, overrides included.
+    pub state: PluginState,
+
+    /// The operator's override, if one is set.
+    pub manual: Option<PluginOverride>,
+
+    /// Decisions within the window.
+    pub decide: CallCounts,
+
+    /// Executions within the window.
+    pub execute: CallCounts,
+
+    /// When the plugin was disabled automatically, if it is.
+    pub disabled_since: Option<DateTime<Utc>>,
+
+    /// When a disabled plugin is probed next.
+    pub next_probe: Option<DateTime<Utc>>,
+
+    /// Times the plugin was disabled automatically since startup.
+    pub disables: u64,
+}
+
+/// Calls and state of one plugin.
+#[derive(Debug, Default)]
+struct Tracker {
+    /// Times of recent decisions and whether they failed, oldest first.
+    decide: VecDeque<(DateTime<Utc>, bool)>,
+
+    /// Times of recent executions and whether they failed, oldest first.
+    execute: VecDeque<(DateTime<Utc>, bool)>,
+
+    /// When the plugin was disabled automatically, if it is.
+    disabled_since: Option<DateTime<Utc>>,
+
+    /// When a disabled plugin is probed next.
+    next_probe: Option<DateTime<Utc>>,
+
+    /// When the probe in flight started.
+    probe_started: Option<DateTime<Utc>>,
+
+    /// The operator's override.
+    manual: Option<PluginOverride>,
+
+    /// Times the plugin was disabled automatically.
+    disables: u64,
+}
+
+impl Tracker {
+    /// Calls of `call` within the window.
+    const fn calls_mut(&mut self, call: PluginCall) -> &mut VecDeque<(DateTime<Utc>, bool)> {
+        match call {
+            PluginCall::Decide => &mut self.decide,
+            PluginCall::Execute => &mut self.execute,
+        }
+    }
+
+    /// Forget calls that left the window.
+    fn prune(&mut self, window: chrono::Duration, now: DateTime<Utc>) {
+        for calls in [&mut self.decide, &mut self.execute] {
+            while calls.front().is_some_and(|(at, _)| now - *at > window) {
+                calls.pop_front();
+            }
+        }
+    }
+
+    const fn state(&self) -> PluginState {
+        match (self.manual, self.disabled_since) {
+            (Some(PluginOverride::Enabled), _) | (None, None) => PluginState::Enabled,
+            (None, Some(_)) if self.probe_started.is_some() => PluginState::Probing,
+            (Some(PluginOverride::Disabled), _) | (None, Some(_)) => PluginState::Disabled,
+        }
+    }
+}
+
+/// Counts the outcome of each plugin's calls, and disables plugins that fail
+/// too often.
+///
+/// # Thread Safety
+///
+/// This struct is NOT thread-safe. Wrap in a `Mutex` or `RwLock` if
+/// concurrent access is needed.
+#[derive(Debug)]
+pub struct PluginHealth {
+    settings: HealthSettings,
+    plugins: HashMap<PluginId, Tracker>,
+}
+
+impl PluginHealth {
+    /// Create a tracker with no calls recorded.
+    #[must_use]
+    pub fn new(settings: HealthSettings) -> Self {
+        Self {
+            settings,
+            plugins: HashMap::new(),
+        }
+    }
+
+    /// Settings the plugins are judged by.
+    #[must_use]
+    pub const fn settings(&self) -> &HealthSettings {
+        &self.settings
+    }
+
+    /// Decide whether `plugin_id` is asked to decide for a wallet at `now`.
+    ///
+    /// A disabled plugin due for a probe is admitted as one, for this wallet
+    /// only: others are left out until the probe's outcome is recorded, or
+    /// the probe has been in flight for a `probe_interval`.
+    pub fn admit(&mut self, plugin_id: &str, now: DateTime<Utc>) -> Admission {
+        let probe_interval = to_chrono(self.settings.probe_interval);
+        let Some(tracker) = self.plugins.get_mut(plugin_id) else {
+            return Admission::Enabled;
+        };
+        match (tracker.manual, tracker.disabled_since) {
+            (Some(PluginOverride::Enabled), _) | (None, None) => Admission::Enabled,
+            (Some(PluginOverride::Disabled), _) => Admission::Disabled,
+            (None, Some(_)) => {
+                let in_flight = tracker
+                    .probe_started
+                    .is_some_and(|started| now - started < probe_interval);
+                if in_flight || tracker.next_probe.is_some_and(|at| now < at) {
+                    return Admission::Disabled;
+                }
+                info!(plugin_id, "Probing disabled plugin");
+                tracker.probe_started = Some(now);
+                Admission::Probe
+            }
+        }
+    }
+
+    /// Record a call of `plugin_id` that succeeded.
+    ///
+    /// A probe that succeeds enables the plugin again, with a clean slate.
+    pub fn record_success(&mut self, plugin_id: &str, call: PluginCall, now: DateTime<Utc>) {
+        let window = to_chrono(self.settings.window);
+        let tracker = self.plugins.entry(plugin_id.to_string()).or_default();
+        tracker.calls_mut(call).push_back((now, false));
+        tracker.prune(window, now);
+
+        if tracker.probe_started.take().is_some() {
+            info!(plugin_id, ?call, "Probe succeeded, enabling plugin again");
+            tracker.disabled_since = None;
+            tracker.next_probe = None;
+            tracker.decide.clear();
+            tracker.execute.clear();
+        }
+    }
+
+    /// Record a call of `plugin_id` that failed with an error of `class`.
+    ///
+    /// A failed probe keeps the plugin disabled until the next one.
+    ///
+    /// # Returns
+    ///
+    /// `true` if this error disabled the plugin, `false` otherwise.
+    pub fn record_error(
+        &mut self,
+        plugin_id: &str,
+        call: PluginCall,
+        class: ErrorClass,
+        now: DateTime<Utc>,
+    ) -> bool {
+        if class == ErrorClass::RateLimited {
+            self.cancel_probe(plugin_id);
+            return false;
+        }
+        let settings = self.settings;
+        let tracker = self.plugins.entry(plugin_id.to_string()).or_default();
+        tracker.calls_mut(call).push_back((now, true));
+        tracker.prune(to_chrono(settings.window), now);
+
+        if tracker.probe_started.take().is_some() {
+            let next = now + to_chrono(settings.probe_interval);
+            warn!(plugin_id, ?call, next_probe = %next, "Probe failed, plugin stays disabled");
+            tracker.next_probe = Some(next);
+            return false;
+        }
+        if tracker.disabled_since.is_some() || !settings.auto_disable {
+            return false;
+        }
+        let failing = [&tracker.decide, &tracker.execute]
+            .into_iter()
+            .map(counts)
+            .find(|counts| exceeds(*counts, &settings));
+        let Some(counts) = failing else {
+            return false;
+        };
+
+        let next = now + to_chrono(settings.probe_interval);
+        warn!(
+            plugin_id,
+            calls = counts.calls,
+            errors = counts.errors,
+            window_secs = settings.window.as_secs(),
+            next_probe = %next,
+            "Plugin failing too often, disabling it"
+        );
+        tracker.disabled_since = Some(now);
+        tracker.next_probe = Some(next);
+        tracker.disables += 1;
+        true
+    }
+
+    /// End the probe of `plugin_id` without an outcome, e.g. because the
+    /// plugin could not act for the wallet; the next wallet probes it.
+    pub fn cancel_probe(&mut self, plugin_id: &str) {
+        if let Some(tracker) = self.plugins.get_mut(plugin_id) {
+            tracker.probe_started = None;
+        }
+    }
+
+    /// Force `plugin_id` on or off, or with `None` return it to its
+    /// automatic state.
+    pub fn set_override(&mut self, plugin_id: &str, manual: Option<PluginOverride>) {
+        let tracker = self.plugins.entry(plugin_id.to_string()).or_default();
+        tracker.manual = manual;
+        tracker.probe_started = None;
+        info!(plugin_id, ?manual, "Plugin override set");
+    }
+
+    /// Until when `plugin_id` is disabled at `now`, if it is.
+    ///
+    /// That is the next probe of an automatically disabled plugin, and a
+    /// `probe_interval` from `now` for one disabled by an operator.
+    #[must_use]
+    pub fn disabled_until(&self, plugin_id: &str, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let tracker = self.plugins.get(plugin_id)?;
+        if tracker.state() == PluginState::Enabled {
+            return None;
+        }
+        let next_probe = tracker
+            .next_probe
+            .filter(|at| *at > now && tracker.manual.is_none());
+        Some(next_probe.unwrap_or_else(|| now + to_chrono(self.settings.probe_interval)))
+    }
+
+    /// Health of `plugin_id` at `now`.
+    #[must_use]
+    pub fn status(&self, plugin_id: &str, now: DateTime<Utc>) -> PluginHealthStatus {
+        let window = to_chrono(self.settings.window);
+        let recent = |calls: &VecDeque<(DateTime<Utc>, bool)>| {
+            let mut counts = CallCounts::default();
+            for (_, failed) in calls.iter().filter(|(at, _)| now - *at <= window) {
+                counts.calls += 1;
+                counts.errors += u32::from(*failed);
+            }
+            counts
+        };
+        self.plugins.get(plugin_id).map_or_else(
+            || PluginHealthStatus {
+                plugin_id: plugin_id.to_string(),
+                state: PluginState::Enabled,
+                manual: None,
+                decide: CallCounts::default(),
+                execute: CallCounts::default(),
+                disabled_since: None,
+                next_probe: None,
+                disables: 0,
+            },
+            |tracker| PluginHealthStatus {
+                plugin_id: plugin_id.to_string(),
+                state: tracker.state(),
+                manual: tracker.manual,
+                decide: recent(&tracker.decide),
+                execute: recent(&tracker.execute),
+                disabled_since: tracker.disabled_since,
+                next_probe: tracker
+                    .next_probe
+                    .filter(|_| tracker.disabled_since.is_some()),
+                disables: tracker.disables,
+            },
+        )
+    }
+}
+
+/// Calls and errors in `calls`.
+fn counts(calls: &VecDeque<(DateTime<Utc>, bool)>) -> CallCounts {
+    let errors = calls.iter().filter(|(_, failed)| *failed).count();
+    CallCounts {
+        calls: u32::try_from(calls.len()).unwrap_or(u32::MAX),
+        errors: u32::try_from(errors).unwrap_or(u32::MAX),
+    }
+}
+
+/// Check whether `counts` fail more often than `settings` allow.
+fn exceeds(counts: CallCounts, settings: &HealthSettings) -> bool {
+    counts.calls >= settings.min_calls.max(1)
+        && f64::from(counts.errors) > settings.max_error_rate * f64::from(counts.calls)
+}
+
+/// Convert a duration for date arithmetic, falling back to one hour if it is too large.
+fn to_chrono(duration: Duration) -> chrono::Duration {
+    chrono::Duration::from_std(duration).unwrap_or_else(|_| chrono::Duration::hours(1))
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn noon() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 1, 1, 12, 0, 0).unwrap()
+    }
+
+    fn secs(secs: i64) -> chrono::Duration {
+        chrono::Duration::seconds(secs)
+    }
+
+    /// Record `n` failed decisions, one a second from `from`; returns
+    /// whether any disabled the plugin.
+    fn fail(health: &mut PluginHealth, n: i64, from: DateTime<Utc>) -> bool {
+        (0..n).fold(false, |disabled, i| {
+            let error = health.record_error(
+                "p",
+                PluginCall::Decide,
+                ErrorClass::Permanent,
+                from + secs(i),
+            );
+            disabled || error
+        })
+    }
+
+    #[test]
+    fn disables_above_error_rate() {
+        let mut health = PluginHealth::new(HealthSettings::default());
+        for i in 0..5 {
+            health.record_success("p", PluginCall::Decide, noon() + secs(i));
+        }
+
+        // 5 of 10 is not above half
+        assert!(!fail(&mut health, 5, noon() + secs(5)));
+        assert_eq!(health.admit("p", noon() + secs(10)), Admission::Enabled);
+
+        assert!(fail(&mut health, 1, noon() + secs(10)));
+        let status = health.status("p", noon() + secs(11));
+        assert_eq!(status.state, PluginState::Disabled);
+        assert_eq!(
+            status.decide,
+            CallCounts {
+                calls: 11,
+                errors: 6
+            }
+        );
+        assert_eq!(status.disables, 1);
+        assert_eq!(health.admit("p", noon() + secs(11)), Admission::Disabled);
+        assert_eq!(
+            health.disabled_until("p", noon() + secs(11)),
+            Some(noon() + secs(310))
+        );
+    }
+
+    #[test]
+    fn old_calls_leave_the_window() {
+        let mut health = PluginHealth::new(HealthSettings::default());
+        assert!(!fail(&mut health, 9, noon()));
+
+        // The first nine failed long ago; one new failure is not enough
+        assert!(!fail(&mut health, 1, noon() + secs(3600)));
+        assert_eq!(health.status("p", noon() + secs(3600)).decide.calls, 1);
+    }
+
+    #[test]
+    fn probes_one_wallet_at_a_time() {
+        let mut health = PluginHealth::new(HealthSettings::default());
+        assert!(fail(&mut health, 10, noon()));
+        let due = noon() + secs(309);
+
+        assert_eq!(health.admit("p", due), Admission::Probe);
+        assert_eq!(health.admit("p", due), Admission::Disabled);
+        assert_eq!(health.status("p", due).state, PluginState::Probing);
+
+        // A failed probe waits for the next interval
+        health.record_error("p", PluginCall::Execute, ErrorClass::Permanent, due);
+        assert_eq!(health.admit("p", due + secs(299)), Admission::Disabled);
+        assert_eq!(health.admit("p", due + secs(300)), Admission::Probe);
+
+        // A cancelled probe is taken up by the next wallet
+        health.cancel_probe("p");
+        assert_eq!(health.admit("p", due + secs(300)), Admission::Probe);
+
+        health.record_success("p", PluginCall::Decide, due + secs(301));
+        let status = health.status("p", due + secs(301));
+        assert_eq!(status.state, PluginState::Enabled);
+        assert_eq!(status.decide, CallCounts::default());
+        assert_eq!(status.next_probe, None);
+        assert_eq!(health.admit("p", due + secs(301)), Admission::Enabled);
+    }
+
+    #[test]
+    fn overrides_beat_automatic_state() {
+        let mut health = PluginHealth::new(HealthSettings::default());
+        health.set_override("p", Some(PluginOverride::Enabled));
+        assert!(fail(&mut health, 10, noon()));
+        assert_eq!(health.admit("p", noon() + secs(10)), Admission::Enabled);
+        assert_eq!(health.disabled_until("p", noon()), None);
+
+        // Back to automatic: disabled, and probed as usual
+        health.set_override("p", None);
+        assert_eq!(health.admit("p", noon() + secs(10)), Admission::Disabled);
+        health.set_override("q", Some(PluginOverride::Disabled));
+        assert_eq!(health.admit("q", noon() + secs(9999)), Admission::Disabled);
+        let status = health.status("q", noon());
+        assert_eq!(status.state, PluginState::Disabled);
+        assert_eq!(status.manual, Some(PluginOverride::Disabled));
+    }
+
+    #[test]
+    fn rate_limits_and_disabled_auto_are_not_counted() {
+        let mut health = PluginHealth::new(HealthSettings {
+            auto_disable: false,
+            ..HealthSettings::default()
+        });
+        assert!(!fail(&mut health, 20, noon()));
+        assert_eq!(health.admit("p", noon() + secs(20)), Admission::Enabled);
+
+        let mut health = PluginHealth::new(HealthSettings::default());
+        for i in 0..20 {
+            health.record_error(
+                "p",
+                PluginCall::Execute,
+                ErrorClass::RateLimited,
+                noon() + secs(i),
+            );
+        }
+        assert_eq!(health.status("p", noon() + secs(20)).execute.calls, 0);
+    }
+}
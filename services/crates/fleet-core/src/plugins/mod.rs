@@ -17,8 +17,9 @@
 //!                              ▼
 //! ┌─────────────────────────────────────────────────────────────┐
 //! │                   PluginRegistry                             │
-//! │  • Stores registered plugins                                │
+//! │  • Stores registered plugins and their priorities           │
 //! │  • Routes actions to correct plugin                         │
+//! │  • Collects candidate actions for the PluginSelector        │
 //! └─────────────────────────────────────────────────────────────┘
 //!                              │
 //!          ┌───────────────────┼───────────────────┐
@@ -33,9 +34,14 @@
 //! └─────────────────┘ └─────────────────┘ └─────────────────┘
 //! ```
 //!
+//! Plugins that keep failing to decide or execute are disabled by
+//! [`PluginHealth`] until a probe finds them working again, or an operator
+//! enables them.
+//!
 //! # Implementing a Plugin
 //!
 //! ```ignore
+//! use fleet_core::action_id;
 //! use fleet_core::plugins::{ActionPlugin, Action, ActionId, ActionResult, PluginContext};
 //! use fleet_core::wallet::WalletState;
 //! use fleet_core::profiles::BehaviorProfile;
@@ -50,7 +56,7 @@
 //!     fn name(&self) -> &str { "My Protocol Plugin" }
 //!     
 //!     fn available_actions(&self) -> Vec<ActionId> {
-//!         vec![ActionId::new("my_plugin.stake")]
+//!         vec![action_id!("my_plugin.stake")]
 //!     }
 //!     
 //!     async fn decide_action(
@@ -83,8 +89,25 @@
 //! }
 //! ```
 
+mod action_id;
+mod cooldown;
+pub mod follow_up;
+mod health;
 mod registry;
+mod selection;
 mod traits;
 
-pub use registry::PluginRegistry;
-pub use traits::{Action, ActionId, ActionPlugin, ActionResult, PluginContext};
+pub use action_id::{ActionId, ActionIdError, checked_action_id, is_valid_action_id};
+pub use cooldown::ActionCooldowns;
+pub use follow_up::{FollowUpAction, FollowUpCondition, MAX_CHAIN_DEPTH, PendingFollowUp};
+pub use health::{
+    Admission, CallCounts, HealthSettings, PluginCall, PluginHealth, PluginHealthStatus,
+    PluginOverride, PluginState,
+};
+pub use registry::{DEFAULT_PRIORITY, Decisions, PluginId, PluginRegistry, Priority};
+pub use selection::{Candidate, PluginSelector, SelectionStrategy};
+pub use traits::{
+    ACTION_REFRESH_BALANCES, Action, ActionError, ActionPlugin, ActionRequirements,
+    ActionResult, ActionStatus, DataFlow, PendingTx, PluginActivity, PluginContext, Replacement,
+    ReplacementOutcome, Submission, SubmissionPath,
+};
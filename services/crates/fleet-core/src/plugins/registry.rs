@@ -1,14 +1,61 @@
 //! Plugin registry for managing action plugins.
 //!
 //! The registry stores plugins and provides methods to query them by ID
-//! or filter by enabled status.
+//! or filter by enabled status. Each plugin has a [`Priority`], and
+//! [`PluginRegistry::decide_all`] collects candidate actions from every plugin
+//! for a [`PluginSelector`](super::PluginSelector) to choose from.
 
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 
-use tracing::warn;
+use tracing::{debug, warn};
 
-use super::traits::{ActionId, ActionPlugin};
+use super::ActionId;
+use super::traits::{Action, ActionPlugin, PluginContext};
+use crate::error::{ErrorClass, FleetError, Result};
+use crate::profiles::BehaviorProfile;
+use crate::wallet::WalletState;
+
+/// Plugin identifier, as returned by [`ActionPlugin::id`].
+pub type PluginId = String;
+
+/// Plugin priority; higher values are preferred.
+pub type Priority = u32;
+
+/// Priority of plugins registered via [`PluginRegistry::register`].
+pub const DEFAULT_PRIORITY: Priority = 100;
+
+/// Outcome of [`PluginRegistry::decide_all`].
+#[derive(Debug, Default)]
+pub struct Decisions {
+    /// One candidate per plugin that decided to act, highest priority first.
+    pub candidates: Vec<(PluginId, Action, Priority)>,
+
+    /// Plugins skipped because they could not possibly act for the wallet.
+    pub prefiltered: Vec<PluginId>,
+
+    /// Plugins that were asked and decided not to act.
+    pub declined: Vec<PluginId>,
+
+    /// Plugins that failed to decide, with the class of their error.
+    pub failed: Vec<(PluginId, ErrorClass)>,
+
+    /// Plugins that did not decide in time, also listed as failed.
+    pub timed_out: Vec<PluginId>,
+
+    /// Plugins left out by the caller.
+    pub skipped: Vec<PluginId>,
+}
+
+/// A registered plugin.
+#[derive(Debug, Clone)]
+struct Entry {
+    plugin: Arc<dyn ActionPlugin>,
+    priority: Priority,
+    /// Registration sequence number, breaks priority ties.
+    seq: u64,
+}
 
 // ═══════════════════════════════════════════════════════════════════════════════
 // PLUGIN REGISTRY
@@ -17,9 +64,13 @@ use super::traits::{ActionId, ActionPlugin};
 /// Registry of available action plugins.
 ///
 /// The registry owns plugins (via `Arc`) and provides methods to:
-/// - Register new plugins
+/// - Register and unregister plugins, optionally with a priority
 /// - Look up plugins by ID
 /// - Get lists of enabled plugins
+/// - Collect candidate actions from every plugin
+///
+/// Plugins are ordered by priority (highest first), then by registration
+/// order.
 ///
 /// # Thread Safety
 ///
@@ -31,7 +82,10 @@ use super::traits::{ActionId, ActionPlugin};
 ///
 /// ```ignore
 /// let mut registry = PluginRegistry::new();
-/// registry.register(Arc::new(MyPlugin::new()));
+/// registry.register(Arc::new(MyPlugin::new()))?;
+/// registry.register_with_priority(Arc::new(SwapPlugin::new()), 50)?;
+///
+/// assert_eq!(registry.plugin_ids(), ["my_plugin", "swap"]);
 ///
 /// // Get a specific plugin
 /// if let Some(plugin) = registry.get("my_plugin") {
@@ -43,7 +97,8 @@ use super::traits::{ActionId, ActionPlugin};
 /// ```
 #[derive(Debug, Default)]
 pub struct PluginRegistry {
-    plugins: HashMap<String, Arc<dyn ActionPlugin>>,
+    plugins: HashMap<String, Entry>,
+    next_seq: u64,
 }
 
 impl PluginRegistry {
@@ -52,22 +107,92 @@ impl PluginRegistry {
     pub fn new() -> Self {
         Self {
             plugins: HashMap::new(),
+            next_seq: 0,
         }
     }
 
-    /// Register a plugin.
+    /// Register a plugin with the [default priority](DEFAULT_PRIORITY).
     ///
     /// If a plugin with the same ID already exists, it is replaced.
-    pub fn register(&mut self, plugin: Arc<dyn ActionPlugin>) {
-        let id = plugin.id().to_string();
-        tracing::info!(plugin_id = %id, plugin_name = %plugin.name(), "Registering plugin");
-        self.plugins.insert(id, plugin);
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FleetError::InvalidConfig`] if the plugin lists an action
+    /// that is not a valid [`ActionId`] or not prefixed with the plugin's ID.
+    pub fn register(&mut self, plugin: Arc<dyn ActionPlugin>) -> Result<()> {
+        self.register_with_priority(plugin, DEFAULT_PRIORITY)
+    }
+
+    /// Register a plugin with a priority.
+    ///
+    /// If a plugin with the same ID already exists, it is replaced.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FleetError::InvalidConfig`] if the plugin lists an action
+    /// that is not a valid [`ActionId`] or not prefixed with the plugin's ID.
+    pub fn register_with_priority(
+        &mut self,
+        plugin: Arc<dyn ActionPlugin>,
+        priority: Priority,
+    ) -> Result<()> {
+        Self::verify_actions(plugin.as_ref())?;
+        tracing::info!(
+            plugin_id = %plugin.id(),
+            plugin_name = %plugin.name(),
+            priority,
+            "Registering plugin"
+        );
+        self.insert(plugin, priority);
+        Ok(())
+    }
+
+    /// Check that every action of `plugin` is a valid action ID of its own.
+    fn verify_actions(plugin: &dyn ActionPlugin) -> Result<()> {
+        let id = plugin.id();
+        for action in plugin.available_actions() {
+            let action = ActionId::parse(action.0)
+                .map_err(|e| FleetError::InvalidConfig(format!("plugin {id}: {e}")))?;
+            if action.plugin_part() != id {
+                return Err(FleetError::InvalidConfig(format!(
+                    "plugin {id} lists action {action}, which is not one of its own"
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Add a verified plugin.
+    fn insert(&mut self, plugin: Arc<dyn ActionPlugin>, priority: Priority) {
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        self.plugins.insert(
+            plugin.id().to_string(),
+            Entry {
+                plugin,
+                priority,
+                seq,
+            },
+        );
+    }
+
+    /// Remove a plugin, returning it if it was registered.
+    pub fn unregister(&mut self, id: &str) -> Option<Arc<dyn ActionPlugin>> {
+        let entry = self.plugins.remove(id)?;
+        tracing::info!(plugin_id = %id, "Unregistered plugin");
+        Some(entry.plugin)
     }
 
     /// Get a plugin by ID.
     #[must_use]
     pub fn get(&self, id: &str) -> Option<&Arc<dyn ActionPlugin>> {
-        self.plugins.get(id)
+        self.plugins.get(id).map(|entry| &entry.plugin)
+    }
+
+    /// Get the priority of a plugin.
+    #[must_use]
+    pub fn priority(&self, id: &str) -> Option<Priority> {
+        self.plugins.get(id).map(|entry| entry.priority)
     }
 
     /// Check if a plugin is registered.
@@ -78,7 +203,7 @@ impl PluginRegistry {
 
     /// Get all registered plugins.
     pub fn all(&self) -> impl Iterator<Item = &Arc<dyn ActionPlugin>> {
-        self.plugins.values()
+        self.plugins.values().map(|entry| &entry.plugin)
     }
 
     /// Get all plugin IDs.
@@ -86,6 +211,25 @@ impl PluginRegistry {
         self.plugins.keys().map(String::as_str)
     }
 
+    /// Get all plugin IDs, highest priority first.
+    #[must_use]
+    pub fn plugin_ids(&self) -> Vec<PluginId> {
+        self.ordered().map(|entry| entry.plugin.id().to_string()).collect()
+    }
+
+    /// Get all registered plugins, highest priority first.
+    #[must_use]
+    pub fn ordered_plugins(&self) -> Vec<Arc<dyn ActionPlugin>> {
+        self.ordered().map(|entry| Arc::clone(&entry.plugin)).collect()
+    }
+
+    /// Entries in priority order.
+    fn ordered(&self) -> impl Iterator<Item = &Entry> {
+        let mut entries: Vec<_> = self.plugins.values().collect();
+        entries.sort_by_key(|entry| (std::cmp::Reverse(entry.priority), entry.seq));
+        entries.into_iter()
+    }
+
     /// Get the number of registered plugins.
     #[must_use]
     pub fn len(&self) -> usize {
@@ -107,7 +251,7 @@ impl PluginRegistry {
         enabled_ids
             .iter()
             .filter_map(|id| {
-                self.plugins.get(id).cloned().or_else(|| {
+                self.get(id).cloned().or_else(|| {
                     warn!(
                         plugin_id = %id,
                         available = ?self.plugins.keys().collect::<Vec<_>>(),
@@ -119,24 +263,120 @@ impl PluginRegistry {
             .collect()
     }
 
+    /// Build a registry holding only the enabled plugins.
+    ///
+    /// Priorities are kept; plugins of equal priority are ordered as in
+    /// `enabled_ids`. Unknown IDs log a warning and are skipped.
+    #[must_use]
+    pub fn subset(&self, enabled_ids: &[String]) -> Self {
+        let mut subset = Self::new();
+        for plugin in self.enabled(enabled_ids) {
+            let priority = self.priority(plugin.id()).unwrap_or(DEFAULT_PRIORITY);
+            subset.insert(plugin, priority);
+        }
+        subset
+    }
+
     /// Get all available actions across all registered plugins.
     #[must_use]
     pub fn all_actions(&self) -> Vec<ActionId> {
-        self.plugins
-            .values()
-            .flat_map(|p| p.available_actions())
-            .collect()
+        self.all().flat_map(|p| p.available_actions()).collect()
     }
 
     /// Find which plugin handles a given action ID.
     #[must_use]
     pub fn find_plugin_for_action(&self, action_id: &ActionId) -> Option<&Arc<dyn ActionPlugin>> {
-        self.plugins.values().find(|p| {
+        self.all().find(|p| {
             p.available_actions()
                 .iter()
                 .any(|a| a.0 == action_id.0)
         })
     }
+
+    /// Ask every plugin for an action, highest priority first.
+    ///
+    /// Returns one candidate per plugin that decided to act. A plugin that
+    /// fails to decide is logged and skipped, so it never keeps the others
+    /// from being considered. Plugins that
+    /// [cannot possibly act](ActionPlugin::can_possibly_act) for the wallet
+    /// are not asked, and reported as prefiltered.
+    pub async fn decide_all(
+        &self,
+        wallet: &WalletState,
+        profile: &BehaviorProfile,
+        context: &mut PluginContext<'_>,
+    ) -> Decisions {
+        self.decide_except(wallet, profile, context, &[], |_| None).await
+    }
+
+    /// Like [`decide_all`](Self::decide_all), but without asking the plugins
+    /// in `skip`, e.g. those disabled for failing too often. They are
+    /// reported as skipped.
+    ///
+    /// A plugin gets as long as `timeout` returns for its ID, if anything, to
+    /// decide. One that takes longer is cancelled and fails with a
+    /// [transient](ErrorClass::Transient) [`FleetError::PluginTimeout`].
+    pub async fn decide_except(
+        &self,
+        wallet: &WalletState,
+        profile: &BehaviorProfile,
+        context: &mut PluginContext<'_>,
+        skip: &[PluginId],
+        timeout: impl Fn(&str) -> Option<Duration> + Send,
+    ) -> Decisions {
+        let mut decisions = Decisions::default();
+
+        for entry in self.ordered() {
+            let plugin = &entry.plugin;
+            if skip.iter().any(|id| id == plugin.id()) {
+                debug!(plugin_id = plugin.id(), "Plugin disabled, skipping");
+                decisions.skipped.push(plugin.id().to_string());
+                continue;
+            }
+            if !plugin.can_possibly_act(wallet, context.now) {
+                debug!(plugin_id = plugin.id(), "Plugin cannot act for wallet, skipping");
+                decisions.prefiltered.push(plugin.id().to_string());
+                continue;
+            }
+            let decided = match timeout(plugin.id()) {
+                Some(limit) => {
+                    tokio::time::timeout(limit, plugin.decide_action(wallet, profile, context))
+                        .await
+                        .unwrap_or_else(|_| Err(FleetError::timeout(plugin.id(), "decide", limit)))
+                }
+                None => plugin.decide_action(wallet, profile, context).await,
+            };
+            match decided {
+                Ok(Some(action)) => {
+                    debug!(
+                        plugin_id = plugin.id(),
+                        action_id = %action.id,
+                        "Plugin decided action"
+                    );
+                    decisions
+                        .candidates
+                        .push((plugin.id().to_string(), action, entry.priority));
+                }
+                Ok(None) => {
+                    debug!(plugin_id = plugin.id(), "Plugin decided no action");
+                    decisions.declined.push(plugin.id().to_string());
+                }
+                Err(e) => {
+                    warn!(
+                        plugin_id = plugin.id(),
+                        error = %e,
+                        "Plugin error during decision"
+                    );
+                    if matches!(e, FleetError::PluginTimeout { .. }) {
+                        decisions.timed_out.push(plugin.id().to_string());
+                    }
+                    decisions.failed.push((plugin.id().to_string(), e.class()));
+                }
+            }
+        }
+
+        decisions
+    }
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -144,14 +384,18 @@ impl PluginRegistry {
 // ═══════════════════════════════════════════════════════════════════════════════
 
 #[cfg(test)]
+#[allow(clippy::expect_used)]
 mod tests {
     use super::*;
     use crate::error::Result;
-    use crate::plugins::traits::{Action, ActionResult, PluginContext};
+    use crate::plugins::traits::{Action, ActionRequirements, ActionResult, PluginContext};
     use crate::profiles::BehaviorProfile;
     use crate::wallet::WalletState;
-    use alloy::primitives::Address;
+    use alloy::primitives::{Address, U256};
     use async_trait::async_trait;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
 
     /// Mock plugin for testing
     #[derive(Debug)]
@@ -159,6 +403,11 @@ mod tests {
         id: String,
         name: String,
         actions: Vec<ActionId>,
+        fails: bool,
+        hangs: bool,
+        declines: bool,
+        requirements: ActionRequirements,
+        decided: AtomicUsize,
     }
 
     impl MockPlugin {
@@ -167,6 +416,39 @@ mod tests {
                 id: id.to_string(),
                 name: format!("Mock {id}"),
                 actions: actions.into_iter().map(ActionId::from).collect(),
+                fails: false,
+                hangs: false,
+                declines: false,
+                requirements: ActionRequirements::none(),
+                decided: AtomicUsize::new(0),
+            }
+        }
+
+        fn failing(id: &str) -> Self {
+            Self {
+                fails: true,
+                ..Self::new(id, vec!["broken.action"])
+            }
+        }
+
+        fn hanging(id: &str) -> Self {
+            Self {
+                hangs: true,
+                ..Self::new(id, vec!["slow.action"])
+            }
+        }
+
+        fn declining(id: &str) -> Self {
+            Self {
+                declines: true,
+                ..Self::new(id, vec!["idle.action"])
+            }
+        }
+
+        fn requiring(id: &str, requirements: ActionRequirements) -> Self {
+            Self {
+                requirements,
+                ..Self::new(id, vec!["gated.action"])
             }
         }
     }
@@ -185,13 +467,33 @@ mod tests {
             self.actions.clone()
         }
 
+        fn requirements(&self, _action: &ActionId) -> ActionRequirements {
+            self.requirements.clone()
+        }
+
         async fn decide_action(
             &self,
             _wallet: &WalletState,
             _profile: &BehaviorProfile,
             _context: &mut PluginContext<'_>,
         ) -> Result<Option<Action>> {
-            Ok(None)
+            self.decided.fetch_add(1, Ordering::Relaxed);
+            if self.hangs {
+                std::future::pending::<()>().await;
+            }
+            if self.fails {
+                return Err(crate::error::FleetError::plugin(
+                    crate::error::ErrorClass::Permanent,
+                    "mock failure",
+                ));
+            }
+            if self.declines {
+                return Ok(None);
+            }
+            Ok(self
+                .actions
+                .first()
+                .map(|action| Action::new(action.as_str(), action.as_str())))
         }
 
         async fn execute_action(
@@ -213,7 +515,7 @@ mod tests {
         let mut registry = PluginRegistry::new();
         let plugin = Arc::new(MockPlugin::new("test", vec!["test.action"]));
 
-        registry.register(plugin);
+        registry.register(plugin).expect("registers");
 
         assert!(registry.contains("test"));
         assert!(!registry.contains("other"));
@@ -222,12 +524,28 @@ mod tests {
         assert_eq!(retrieved.id(), "test");
     }
 
+    #[test]
+    fn register_rejects_malformed_or_foreign_actions() {
+        let mut registry = PluginRegistry::new();
+        for (id, action) in [("a", "a"), ("a", "a.Act"), ("a", "a."), ("a", "b.act")] {
+            let plugin = Arc::new(MockPlugin::new(id, vec!["a.ok", action]));
+            let error = registry.register(plugin).expect_err(action);
+            assert!(matches!(error, FleetError::InvalidConfig(_)), "{error}");
+        }
+        assert!(registry.is_empty());
+
+        registry
+            .register(Arc::new(MockPlugin::new("a", vec!["a.ok", "a.other"])))
+            .expect("registers");
+        assert!(registry.contains("a"));
+    }
+
     #[test]
     fn enabled_filters_correctly() {
         let mut registry = PluginRegistry::new();
-        registry.register(Arc::new(MockPlugin::new("a", vec![])));
-        registry.register(Arc::new(MockPlugin::new("b", vec![])));
-        registry.register(Arc::new(MockPlugin::new("c", vec![])));
+        registry.register(Arc::new(MockPlugin::new("a", vec![]))).expect("registers");
+        registry.register(Arc::new(MockPlugin::new("b", vec![]))).expect("registers");
+        registry.register(Arc::new(MockPlugin::new("c", vec![]))).expect("registers");
 
         let enabled = registry.enabled(&["a".to_string(), "c".to_string()]);
         assert_eq!(enabled.len(), 2);
@@ -242,8 +560,10 @@ mod tests {
     #[test]
     fn all_actions_aggregates() {
         let mut registry = PluginRegistry::new();
-        registry.register(Arc::new(MockPlugin::new("a", vec!["a.one", "a.two"])));
-        registry.register(Arc::new(MockPlugin::new("b", vec!["b.one"])));
+        registry
+            .register(Arc::new(MockPlugin::new("a", vec!["a.one", "a.two"])))
+            .expect("registers");
+        registry.register(Arc::new(MockPlugin::new("b", vec!["b.one"]))).expect("registers");
 
         let actions = registry.all_actions();
         assert_eq!(actions.len(), 3);
@@ -252,8 +572,8 @@ mod tests {
     #[test]
     fn find_plugin_for_action() {
         let mut registry = PluginRegistry::new();
-        registry.register(Arc::new(MockPlugin::new("a", vec!["a.action"])));
-        registry.register(Arc::new(MockPlugin::new("b", vec!["b.action"])));
+        registry.register(Arc::new(MockPlugin::new("a", vec!["a.action"]))).expect("registers");
+        registry.register(Arc::new(MockPlugin::new("b", vec!["b.action"]))).expect("registers");
 
         let plugin = registry
             .find_plugin_for_action(&ActionId::from("a.action"))
@@ -264,4 +584,150 @@ mod tests {
             .find_plugin_for_action(&ActionId::from("unknown"))
             .is_none());
     }
+
+    #[test]
+    fn priorities_order_plugins() {
+        let mut registry = PluginRegistry::new();
+        registry.register(Arc::new(MockPlugin::new("a", vec![]))).expect("registers");
+        registry
+            .register_with_priority(Arc::new(MockPlugin::new("b", vec![])), 200)
+            .expect("registers");
+        registry.register(Arc::new(MockPlugin::new("c", vec![]))).expect("registers");
+
+        assert_eq!(registry.plugin_ids(), ["b", "a", "c"]);
+        assert_eq!(registry.priority("b"), Some(200));
+        assert_eq!(registry.priority("a"), Some(DEFAULT_PRIORITY));
+
+        // The subset keeps priorities and orders ties as enabled
+        let subset = registry.subset(&["c".to_string(), "a".to_string(), "b".to_string()]);
+        assert_eq!(subset.plugin_ids(), ["b", "c", "a"]);
+    }
+
+    #[test]
+    fn unregister_removes_plugin() {
+        let mut registry = PluginRegistry::new();
+        registry.register(Arc::new(MockPlugin::new("a", vec!["a.action"]))).expect("registers");
+
+        let removed = registry.unregister("a").expect("should remove");
+        assert_eq!(removed.id(), "a");
+        assert!(registry.is_empty());
+        assert!(registry.unregister("a").is_none());
+    }
+
+    #[tokio::test]
+    async fn decide_all_skips_failing_plugins() {
+        let mut registry = PluginRegistry::new();
+        registry
+            .register_with_priority(Arc::new(MockPlugin::failing("broken")), 300)
+            .expect("registers");
+        registry.register(Arc::new(MockPlugin::new("a", vec!["a.action"]))).expect("registers");
+        registry
+            .register_with_priority(Arc::new(MockPlugin::new("b", vec!["b.action"])), 200)
+            .expect("registers");
+        registry.register(Arc::new(MockPlugin::declining("idle"))).expect("registers");
+
+        let wallet = WalletState::new("test".into(), Address::ZERO);
+        let mut rng = StdRng::seed_from_u64(42);
+        let config = serde_json::Value::Null;
+        let mut context = PluginContext::new(chrono::Utc::now(), &mut rng, &config);
+
+        let decisions = registry
+            .decide_all(&wallet, &BehaviorProfile::new("test"), &mut context)
+            .await;
+
+        let summary: Vec<_> = decisions
+            .candidates
+            .iter()
+            .map(|(id, action, priority)| (id.as_str(), action.id.as_str(), *priority))
+            .collect();
+        assert_eq!(summary, [("b", "b.action", 200), ("a", "a.action", 100)]);
+        assert_eq!(decisions.declined, ["idle"]);
+        assert_eq!(decisions.failed.len(), 1);
+        assert_eq!(decisions.failed[0].0, "broken");
+    }
+
+    #[tokio::test]
+    async fn decide_except_leaves_out_skipped_plugins() {
+        let mut registry = PluginRegistry::new();
+        registry
+            .register_with_priority(Arc::new(MockPlugin::new("a", vec!["a.action"])), 200)
+            .expect("registers");
+        registry.register(Arc::new(MockPlugin::new("b", vec!["b.action"]))).expect("registers");
+
+        let wallet = WalletState::new("test".into(), Address::ZERO);
+        let mut rng = StdRng::seed_from_u64(42);
+        let config = serde_json::Value::Null;
+        let mut context = PluginContext::new(chrono::Utc::now(), &mut rng, &config);
+
+        let decisions = registry
+            .decide_except(
+                &wallet,
+                &BehaviorProfile::new("test"),
+                &mut context,
+                &["a".to_string()],
+                |_| None,
+            )
+            .await;
+
+        assert_eq!(decisions.skipped, ["a"]);
+        assert_eq!(decisions.candidates.len(), 1);
+        assert_eq!(decisions.candidates[0].0, "b");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn decide_except_cancels_plugins_that_take_too_long() {
+        let mut registry = PluginRegistry::new();
+        registry
+            .register_with_priority(Arc::new(MockPlugin::hanging("slow")), 200)
+            .expect("registers");
+        registry.register(Arc::new(MockPlugin::new("a", vec!["a.action"]))).expect("registers");
+
+        let wallet = WalletState::new("test".into(), Address::ZERO);
+        let mut rng = StdRng::seed_from_u64(42);
+        let config = serde_json::Value::Null;
+        let mut context = PluginContext::new(chrono::Utc::now(), &mut rng, &config);
+
+        let decisions = registry
+            .decide_except(&wallet, &BehaviorProfile::new("test"), &mut context, &[], |_| {
+                Some(Duration::from_secs(10))
+            })
+            .await;
+
+        assert_eq!(decisions.timed_out, ["slow"]);
+        assert_eq!(decisions.failed, [("slow".to_string(), ErrorClass::Transient)]);
+        assert_eq!(decisions.candidates.len(), 1);
+        assert_eq!(decisions.candidates[0].0, "a");
+    }
+
+    #[tokio::test]
+    async fn decide_all_prefilters_plugins_that_cannot_act() {
+        let token = Address::repeat_byte(0xDA);
+        let requirements = ActionRequirements::none()
+            .with_native_balance(U256::from(1))
+            .with_token_balance(token, U256::from(100));
+        let gated = Arc::new(MockPlugin::requiring("gated", requirements));
+        let mut registry = PluginRegistry::new();
+        registry.register(Arc::clone(&gated) as Arc<dyn ActionPlugin>).expect("registers");
+
+        let mut rng = StdRng::seed_from_u64(42);
+        let config = serde_json::Value::Null;
+        let profile = BehaviorProfile::new("test");
+
+        // No balances: never asked to decide
+        let mut wallet = WalletState::new("test".into(), Address::ZERO);
+        let mut context = PluginContext::new(chrono::Utc::now(), &mut rng, &config);
+        let decisions = registry.decide_all(&wallet, &profile, &mut context).await;
+        assert!(decisions.candidates.is_empty());
+        assert_eq!(decisions.prefiltered, ["gated"]);
+        assert_eq!(gated.decided.load(Ordering::Relaxed), 0);
+
+        // Funded: passes through
+        wallet.native_balance = U256::from(1);
+        wallet.set_token_balance(token, U256::from(100), 1);
+        let mut context = PluginContext::new(chrono::Utc::now(), &mut rng, &config);
+        let decisions = registry.decide_all(&wallet, &profile, &mut context).await;
+        assert!(decisions.prefiltered.is_empty());
+        assert_eq!(decisions.candidates.len(), 1);
+        assert_eq!(gated.decided.load(Ordering::Relaxed), 1);
+    }
 }
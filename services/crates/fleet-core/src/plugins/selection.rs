@@ -0,0 +1,300 @@
+//! Choosing between the candidate actions of several plugins.
+//!
+//! With more than one plugin enabled, several may want to act for the same
+//! wallet on the same tick. [`PluginRegistry::decide_all`] collects their
+//! candidates, and a [`PluginSelector`] picks one according to the configured
+//! [`SelectionStrategy`].
+//!
+//! [`PluginRegistry::decide_all`]: super::PluginRegistry::decide_all
+
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use super::registry::{PluginId, Priority};
+use super::traits::Action;
+
+/// A candidate action: the plugin that decided it, the action and the
+/// plugin's priority.
+pub type Candidate = (PluginId, Action, Priority);
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// SELECTION STRATEGY
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// How to choose between candidate actions of several plugins.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SelectionStrategy {
+    /// The candidate of the highest priority plugin wins.
+    ///
+    /// Ties go to the plugin that comes first in registry order.
+    #[default]
+    HighestPriority,
+
+    /// A random candidate wins, weighted by plugin priority.
+    ///
+    /// If every candidate has priority 0 they are equally likely.
+    WeightedRandom,
+
+    /// Plugins take turns per wallet.
+    ///
+    /// The first candidate after the wallet's previously chosen plugin, in
+    /// registry order, wins.
+    RoundRobin,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// PLUGIN SELECTOR
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Picks one candidate action per decision.
+///
+/// # Example
+///
+/// ```
+/// use fleet_core::plugins::{Action, PluginSelector, SelectionStrategy};
+/// use rand::SeedableRng;
+/// use rand::rngs::StdRng;
+///
+/// let mut selector = PluginSelector::new(SelectionStrategy::RoundRobin);
+/// let order = ["ghostnet".to_string(), "swap".to_string()];
+/// let candidates = || {
+///     vec![
+///         ("ghostnet".to_string(), Action::new("ghostnet.jack_in", "Jack In"), 100),
+///         ("swap".to_string(), Action::new("swap.swap", "Swap"), 100),
+///     ]
+/// };
+/// let mut rng = StdRng::seed_from_u64(42);
+///
+/// let first = selector.select("wallet_1", candidates(), &order, &mut rng);
+/// let second = selector.select("wallet_1", candidates(), &order, &mut rng);
+/// assert_eq!(first.map(|(id, _, _)| id).as_deref(), Some("ghostnet"));
+/// assert_eq!(second.map(|(id, _, _)| id).as_deref(), Some("swap"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PluginSelector {
+    /// Strategy in use.
+    strategy: SelectionStrategy,
+
+    /// Plugin chosen last, per wallet ID.
+    last_chosen: HashMap<String, PluginId>,
+}
+
+impl PluginSelector {
+    /// Create a selector using the given strategy.
+    #[must_use]
+    pub fn new(strategy: SelectionStrategy) -> Self {
+        Self {
+            strategy,
+            last_chosen: HashMap::new(),
+        }
+    }
+
+    /// Strategy in use.
+    #[must_use]
+    pub const fn strategy(&self) -> SelectionStrategy {
+        self.strategy
+    }
+
+    /// Choose one of the candidates for a wallet.
+    ///
+    /// `order` is the registry order of all plugins (see
+    /// [`PluginRegistry::plugin_ids`](super::PluginRegistry::plugin_ids)).
+    /// Returns `None` if there are no candidates.
+    pub fn select<R: Rng + ?Sized>(
+        &mut self,
+        wallet_id: &str,
+        candidates: Vec<Candidate>,
+        order: &[PluginId],
+        rng: &mut R,
+    ) -> Option<Candidate> {
+        let chosen = match self.strategy {
+            SelectionStrategy::HighestPriority => Self::highest_priority(candidates, order),
+            SelectionStrategy::WeightedRandom => Self::weighted_random(candidates, rng),
+            SelectionStrategy::RoundRobin => {
+                let last = self.last_chosen.get(wallet_id).map(String::as_str);
+                Self::round_robin(candidates, order, last)
+            }
+        }?;
+
+        self.last_chosen
+            .insert(wallet_id.to_string(), chosen.0.clone());
+        Some(chosen)
+    }
+
+    /// Highest priority, ties broken by registry order.
+    fn highest_priority(candidates: Vec<Candidate>, order: &[PluginId]) -> Option<Candidate> {
+        candidates
+            .into_iter()
+            .min_by_key(|(id, _, priority)| (Reverse(*priority), position(order, id)))
+    }
+
+    /// Random, weighted by priority.
+    fn weighted_random<R: Rng + ?Sized>(
+        mut candidates: Vec<Candidate>,
+        rng: &mut R,
+    ) -> Option<Candidate> {
+        if candidates.is_empty() {
+            return None;
+        }
+
+        let total: u64 = candidates.iter().map(|(_, _, p)| u64::from(*p)).sum();
+        if total == 0 {
+            let index = rng.random_range(0..candidates.len());
+            return Some(candidates.swap_remove(index));
+        }
+
+        let mut roll = rng.random_range(0..total);
+        let index = candidates
+            .iter()
+            .position(|(_, _, priority)| {
+                let weight = u64::from(*priority);
+                if roll < weight {
+                    return true;
+                }
+                roll -= weight;
+                false
+            })
+            .unwrap_or(candidates.len() - 1);
+        Some(candidates.swap_remove(index))
+    }
+
+    /// First candidate after the last chosen plugin, wrapping around.
+    fn round_robin(
+        candidates: Vec<Candidate>,
+        order: &[PluginId],
+        last: Option<&str>,
+    ) -> Option<Candidate> {
+        let len = order.len().max(1);
+        let start = last
+            .and_then(|last| order.iter().position(|id| id == last))
+            .map_or(0, |index| index + 1);
+
+        candidates.into_iter().min_by_key(|(id, _, _)| {
+            // Plugins missing from the order go last
+            order
+                .iter()
+                .position(|other| other == id)
+                .map_or(usize::MAX, |index| (index + len - start % len) % len)
+        })
+    }
+}
+
+/// Position of a plugin in registry order; unknown plugins go last.
+fn position(order: &[PluginId], id: &str) -> usize {
+    order
+        .iter()
+        .position(|other| other == id)
+        .unwrap_or(usize::MAX)
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+#[allow(clippy::expect_used)]
+mod tests {
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    use super::*;
+
+    fn order() -> Vec<PluginId> {
+        vec!["a".into(), "b".into()]
+    }
+
+    fn candidates(priority_a: Priority, priority_b: Priority) -> Vec<Candidate> {
+        vec![
+            ("a".into(), Action::new("a.act", "A"), priority_a),
+            ("b".into(), Action::new("b.act", "B"), priority_b),
+        ]
+    }
+
+    fn chosen(
+        selector: &mut PluginSelector,
+        wallet: &str,
+        candidates: Vec<Candidate>,
+        rng: &mut StdRng,
+    ) -> PluginId {
+        selector
+            .select(wallet, candidates, &order(), rng)
+            .expect("should choose a candidate")
+            .0
+    }
+
+    #[test]
+    fn highest_priority_wins() {
+        let mut selector = PluginSelector::new(SelectionStrategy::HighestPriority);
+        let mut rng = StdRng::seed_from_u64(42);
+
+        assert_eq!(
+            chosen(&mut selector, "w", candidates(100, 200), &mut rng),
+            "b"
+        );
+        // Ties go to registry order
+        assert_eq!(
+            chosen(&mut selector, "w", candidates(100, 100), &mut rng),
+            "a"
+        );
+        assert!(selector.select("w", vec![], &order(), &mut rng).is_none());
+    }
+
+    #[test]
+    fn weighted_random_follows_priorities() {
+        let mut selector = PluginSelector::new(SelectionStrategy::WeightedRandom);
+        let mut rng = StdRng::seed_from_u64(42);
+
+        let picks_of_b = (0..1000)
+            .filter(|_| chosen(&mut selector, "w", candidates(100, 300), &mut rng) == "b")
+            .count();
+        assert!(
+            (650..=850).contains(&picks_of_b),
+            "b picked {picks_of_b} times"
+        );
+
+        // Zero priority is never picked over a weighted candidate
+        assert!((0..100).all(|_| chosen(&mut selector, "w", candidates(0, 1), &mut rng) == "b"));
+
+        // Same seed, same choices
+        let run = |seed| {
+            let mut selector = PluginSelector::new(SelectionStrategy::WeightedRandom);
+            let mut rng = StdRng::seed_from_u64(seed);
+            (0..20)
+                .map(|_| chosen(&mut selector, "w", candidates(0, 0), &mut rng))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(run(7), run(7));
+    }
+
+    #[test]
+    fn round_robin_alternates_per_wallet() {
+        let mut selector = PluginSelector::new(SelectionStrategy::RoundRobin);
+        let mut rng = StdRng::seed_from_u64(42);
+
+        assert_eq!(
+            chosen(&mut selector, "w1", candidates(100, 100), &mut rng),
+            "a"
+        );
+        assert_eq!(
+            chosen(&mut selector, "w1", candidates(100, 100), &mut rng),
+            "b"
+        );
+        assert_eq!(
+            chosen(&mut selector, "w1", candidates(100, 100), &mut rng),
+            "a"
+        );
+        // Each wallet has its own turn
+        assert_eq!(
+            chosen(&mut selector, "w2", candidates(100, 100), &mut rng),
+            "a"
+        );
+
+        // Only candidates are considered: "b" is skipped when it has nothing to do
+        let only_a = vec![("a".into(), Action::new("a.act", "A"), 100)];
+        assert_eq!(chosen(&mut selector, "w1", only_a, &mut rng), "a");
+    }
+}
@@ -2,61 +2,38 @@
 //!
 //! This module defines the [`ActionPlugin`] trait that all action plugins must implement.
 
+use std::collections::HashMap;
 use std::fmt::Debug;
 
-use alloy::primitives::{Address, Bytes, TxHash};
+use alloy::primitives::{Address, Bytes, TxHash, U256};
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
 
+use super::{ActionCooldowns, ActionId, FollowUpAction, checked_action_id};
 use crate::error::Result;
 use crate::profiles::BehaviorProfile;
-use crate::wallet::WalletState;
+use crate::safety::Spend;
+use crate::wallet::{PluginState, WalletState, decode_plugin_state};
 
 // ═══════════════════════════════════════════════════════════════════════════════
 // ACTION TYPES
 // ═══════════════════════════════════════════════════════════════════════════════
 
-/// Unique identifier for an action type.
-///
-/// Format is typically `plugin_id.action_name`, e.g., "ghostnet.jack_in".
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct ActionId(pub String);
-
-impl ActionId {
-    /// Create a new action ID.
-    #[must_use]
-    pub fn new(id: impl Into<String>) -> Self {
-        Self(id.into())
-    }
-
-    /// Get the action ID as a string slice.
-    #[must_use]
-    pub fn as_str(&self) -> &str {
-        &self.0
-    }
-}
-
-impl std::fmt::Display for ActionId {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0)
-    }
-}
-
-impl From<&str> for ActionId {
-    fn from(s: &str) -> Self {
-        Self(s.to_string())
-    }
-}
-
-impl From<String> for ActionId {
-    fn from(s: String) -> Self {
-        Self(s)
-    }
-}
+/// Action ID of a [balance refresh request](Action::refresh_balances).
+pub const ACTION_REFRESH_BALANCES: &str = checked_action_id("fleet.refresh_balances");
 
 /// An action that can be executed on-chain.
 ///
 /// Actions are created by plugins during the decision phase and executed
 /// by the orchestrator.
+///
+/// A plugin that cannot decide because the balances it needs are stale can
+/// return [`Action::refresh_balances`] instead. The orchestrator handles that
+/// action itself by re-reading the balances; it is never passed back to the
+/// plugin for execution.
+///
+/// An action can carry [follow-ups](super::follow_up) to run after it, e.g.
+/// the stake after an approval.
 #[derive(Debug, Clone)]
 pub struct Action {
     /// Unique identifier for this action type.
@@ -67,6 +44,12 @@ pub struct Action {
 
     /// Action-specific data (plugin interprets this).
     pub data: serde_json::Value,
+
+    /// Actions to run after this one, depending on its outcome.
+    pub follow_up: Vec<FollowUpAction>,
+
+    /// Actions of a follow-up chain before this one; 0 for decided actions.
+    pub chain_depth: u32,
 }
 
 impl Action {
@@ -77,6 +60,8 @@ impl Action {
             id: id.into(),
             name: name.into(),
             data: serde_json::Value::Null,
+            follow_up: Vec::new(),
+            chain_depth: 0,
         }
     }
 
@@ -87,15 +72,277 @@ impl Action {
             id: id.into(),
             name: name.into(),
             data,
+            follow_up: Vec::new(),
+            chain_depth: 0,
+        }
+    }
+
+    /// Add an action to run after this one.
+    #[must_use]
+    pub fn with_follow_up(mut self, follow_up: FollowUpAction) -> Self {
+        self.follow_up.push(follow_up);
+        self
+    }
+
+    /// Create a request for the orchestrator to re-read the given token balances.
+    #[must_use]
+    pub fn refresh_balances(tokens: &[Address]) -> Self {
+        Self::with_data(
+            ACTION_REFRESH_BALANCES,
+            "Refresh Balances",
+            serde_json::json!({ "tokens": tokens }),
+        )
+    }
+
+    /// Check whether this is a [balance refresh request](Self::refresh_balances).
+    #[must_use]
+    pub fn is_refresh_request(&self) -> bool {
+        self.id.as_str() == ACTION_REFRESH_BALANCES
+    }
+
+    /// Tokens to re-read if this is a [balance refresh request](Self::refresh_balances).
+    #[must_use]
+    pub fn refresh_tokens(&self) -> Option<Vec<Address>> {
+        if !self.is_refresh_request() {
+            return None;
+        }
+        serde_json::from_value(self.data["tokens"].clone()).ok()
+    }
+}
+
+/// Outcome category of an executed action.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActionStatus {
+    /// The transaction was mined and succeeded.
+    Succeeded,
+
+    /// The transaction was mined but reverted.
+    Reverted,
+
+    /// The action never made it on chain: it was not sent, or was sent and
+    /// dropped before inclusion.
+    Dropped,
+
+    /// The plugin decided not to act after all (e.g. a precondition no longer
+    /// holds). Not an error.
+    Skipped,
+
+    /// The action was only simulated (dry run).
+    Simulated,
+}
+
+impl ActionStatus {
+    /// Check whether the action succeeded on chain.
+    #[must_use]
+    pub const fn is_success(self) -> bool {
+        matches!(self, Self::Succeeded)
+    }
+
+    /// Check whether the action failed (reverted or dropped).
+    #[must_use]
+    pub const fn is_failure(self) -> bool {
+        matches!(self, Self::Reverted | Self::Dropped)
+    }
+
+    /// Name of the status as used in serialized form.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Succeeded => "succeeded",
+            Self::Reverted => "reverted",
+            Self::Dropped => "dropped",
+            Self::Skipped => "skipped",
+            Self::Simulated => "simulated",
+        }
+    }
+}
+
+impl std::fmt::Display for ActionStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// How a transaction that was not mined in time was settled.
+///
+/// See [`ActionPlugin::build_replacement`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReplacementOutcome {
+    /// The original transaction was mined after all.
+    OriginalMined,
+
+    /// A replacement built by the plugin was mined in its place.
+    ReplacementMined,
+
+    /// A cancel transaction (a self-transfer) was mined in its place; the
+    /// action did not happen.
+    Cancelled,
+
+    /// No transaction was mined before the replacements ran out.
+    Abandoned,
+}
+
+impl ReplacementOutcome {
+    /// Name of the outcome as used in serialized form.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::OriginalMined => "original_mined",
+            Self::ReplacementMined => "replacement_mined",
+            Self::Cancelled => "cancelled",
+            Self::Abandoned => "abandoned",
+        }
+    }
+
+    /// Check whether a transaction with the action's nonce was mined.
+    #[must_use]
+    pub const fn used_nonce(self) -> bool {
+        !matches!(self, Self::Abandoned)
+    }
+}
+
+impl std::fmt::Display for ReplacementOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Replacements sent for a transaction that was not mined in time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Replacement {
+    /// How the transaction was settled.
+    pub outcome: ReplacementOutcome,
+
+    /// Replacement and cancel transactions sent.
+    pub attempts: u32,
+}
+
+/// How a transaction was submitted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SubmissionPath {
+    /// Through the chain's realtime API, which answers with the receipt.
+    Realtime,
+
+    /// Sent, then polled for the receipt.
+    Standard,
+}
+
+impl SubmissionPath {
+    /// Name of the path as used in serialized form.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Realtime => "realtime",
+            Self::Standard => "standard",
+        }
+    }
+}
+
+impl std::fmt::Display for SubmissionPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// How the transaction of an action was submitted by the engine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Submission {
+    /// Path the transaction took.
+    pub path: SubmissionPath,
+
+    /// Time from submitting the transaction to holding its receipt in
+    /// milliseconds, if it was mined in time.
+    pub receipt_latency_ms: Option<u64>,
+}
+
+/// A sent transaction that has not been mined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PendingTx {
+    /// Hash of the original transaction.
+    pub tx_hash: TxHash,
+
+    /// Nonce the original was sent with, and any replacement must use.
+    pub nonce: u64,
+
+    /// Replacements already sent for it.
+    pub replacements: u32,
+
+    /// Gas price the original was sent with, in wei, if known.
+    pub gas_price: Option<u128>,
+}
+
+/// Why an action failed, and whether retrying it may help.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActionError {
+    /// Human-readable error message.
+    pub message: String,
+
+    /// Whether the same action may succeed if retried (e.g. after a dropped
+    /// transaction or an RPC timeout).
+    pub retryable: bool,
+}
+
+impl ActionError {
+    /// Create an error that retrying will not fix.
+    #[must_use]
+    pub fn permanent(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            retryable: false,
+        }
+    }
+
+    /// Create an error that retrying may fix.
+    #[must_use]
+    pub fn retryable(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            retryable: true,
         }
     }
 }
 
+impl std::fmt::Display for ActionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)?;
+        if self.retryable {
+            f.write_str(" (retryable)")?;
+        }
+        Ok(())
+    }
+}
+
 /// Result of executing an action.
-#[derive(Debug, Clone)]
+///
+/// Besides the [`status`](Self::status), results carry whatever is known
+/// about the transaction and a plugin-specific [`detail`](Self::detail)
+/// payload (e.g. the amount staked or the level entered).
+///
+/// # Example
+///
+/// ```
+/// use alloy::primitives::TxHash;
+/// use fleet_core::plugins::{ActionResult, ActionStatus};
+///
+/// let result = ActionResult::success_with_gas(TxHash::ZERO, 120_000)
+///     .with_block_number(42)
+///     .with_effective_gas_price(1_000_000_000)
+///     .with_detail(serde_json::json!({ "level": 3 }));
+///
+/// assert_eq!(result.status, ActionStatus::Succeeded);
+/// assert_eq!(result.gas_cost_wei(), Some(120_000_000_000_000));
+///
+/// let failed = ActionResult::failure("nonce too low");
+/// assert!(failed.status.is_failure());
+/// assert!(!failed.is_retryable());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ActionResult {
-    /// Whether the action was successful.
-    pub success: bool,
+    /// Outcome category.
+    pub status: ActionStatus,
 
     /// Transaction hash if a transaction was sent.
     pub tx_hash: Option<TxHash>,
@@ -103,52 +350,387 @@ pub struct ActionResult {
     /// Gas used if known.
     pub gas_used: Option<u64>,
 
-    /// Error message if the action failed.
-    pub error: Option<String>,
+    /// Gas the transaction was estimated to use before it was sent, if
+    /// estimated.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_estimate: Option<u64>,
+
+    /// Price paid per unit of gas (in wei) if known.
+    pub effective_gas_price: Option<u128>,
+
+    /// Block the transaction was included in, if known.
+    pub block_number: Option<u64>,
+
+    /// Time taken to execute the action in milliseconds, if measured.
+    pub duration_ms: Option<u64>,
+
+    /// Plugin-specific payload (`null` if none).
+    #[serde(default)]
+    pub detail: serde_json::Value,
+
+    /// Error if the action failed.
+    pub error: Option<ActionError>,
+
+    /// Replacements sent because the transaction was not mined in time.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replacement: Option<Replacement>,
+
+    /// How the engine submitted the transaction, if it did: replacements and
+    /// sweeps, not the transactions plugins send for their actions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub submission: Option<Submission>,
+
+    /// Plugin state derived from the receipt, replacing the next `read_state`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub plugin_state: Option<serde_json::Value>,
+
+    /// Tokens the mined transaction moved, as its receipt shows.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub data_flows: Vec<DataFlow>,
 }
 
 impl ActionResult {
-    /// Create a successful result.
+    /// Create a result with the given status and nothing else known.
     #[must_use]
-    pub const fn success(tx_hash: TxHash) -> Self {
+    pub const fn with_status(status: ActionStatus) -> Self {
         Self {
-            success: true,
-            tx_hash: Some(tx_hash),
+            status,
+            tx_hash: None,
             gas_used: None,
+            gas_estimate: None,
+            effective_gas_price: None,
+            block_number: None,
+            duration_ms: None,
+            detail: serde_json::Value::Null,
             error: None,
+            replacement: None,
+            submission: None,
+            plugin_state: None,
+            data_flows: Vec::new(),
         }
     }
 
+    /// Create a successful result.
+    #[must_use]
+    pub const fn success(tx_hash: TxHash) -> Self {
+        let mut result = Self::with_status(ActionStatus::Succeeded);
+        result.tx_hash = Some(tx_hash);
+        result
+    }
+
     /// Create a successful result with gas info.
     #[must_use]
     pub const fn success_with_gas(tx_hash: TxHash, gas_used: u64) -> Self {
-        Self {
-            success: true,
-            tx_hash: Some(tx_hash),
-            gas_used: Some(gas_used),
-            error: None,
-        }
+        Self::success(tx_hash).with_gas_used(gas_used)
     }
 
-    /// Create a failed result.
+    /// Create a result from a mined transaction's receipt.
+    ///
+    /// The status is [`Succeeded`](ActionStatus::Succeeded) or
+    /// [`Reverted`](ActionStatus::Reverted) depending on the receipt.
+    #[must_use]
+    pub fn from_receipt(receipt: &evm_provider::TransactionReceipt) -> Self {
+        let mut result = if receipt.success {
+            Self::success(receipt.tx_hash)
+        } else {
+            Self::reverted(receipt.tx_hash, "transaction reverted")
+        };
+        result.gas_used = Some(receipt.gas_used);
+        result.block_number = Some(receipt.block_number);
+        result
+    }
+
+    /// Create a failed result for an action that never made it on chain.
+    ///
+    /// The error is not retryable; use [`with_error`](Self::with_error) with
+    /// an [`ActionError::retryable`] to mark it otherwise.
     #[must_use]
     pub fn failure(error: impl Into<String>) -> Self {
-        Self {
-            success: false,
-            tx_hash: None,
-            gas_used: None,
-            error: Some(error.into()),
-        }
+        Self::with_status(ActionStatus::Dropped).with_error(ActionError::permanent(error))
+    }
+
+    /// Create a failed result for a transaction that was sent but dropped
+    /// before inclusion. Retrying may help.
+    #[must_use]
+    pub fn dropped(tx_hash: TxHash, error: impl Into<String>) -> Self {
+        let mut result =
+            Self::with_status(ActionStatus::Dropped).with_error(ActionError::retryable(error));
+        result.tx_hash = Some(tx_hash);
+        result
     }
 
     /// Create a failed result with a transaction that reverted.
     #[must_use]
     pub fn reverted(tx_hash: TxHash, error: impl Into<String>) -> Self {
-        Self {
-            success: false,
-            tx_hash: Some(tx_hash),
-            gas_used: None,
-            error: Some(error.into()),
+        let mut result =
+            Self::with_status(ActionStatus::Reverted).with_error(ActionError::permanent(error));
+        result.tx_hash = Some(tx_hash);
+        result
+    }
+
+    /// Create a result for an action the plugin decided not to perform.
+    #[must_use]
+    pub fn skipped(reason: impl Into<String>) -> Self {
+        Self::with_status(ActionStatus::Skipped)
+            .with_detail(serde_json::json!({ "reason": reason.into() }))
+    }
+
+    /// Create a result for an action that was only simulated.
+    #[must_use]
+    pub const fn simulated() -> Self {
+        Self::with_status(ActionStatus::Simulated)
+    }
+
+    /// Set the gas used.
+    #[must_use]
+    pub const fn with_gas_used(mut self, gas_used: u64) -> Self {
+        self.gas_used = Some(gas_used);
+        self
+    }
+
+    /// Set the gas the transaction was estimated to use.
+    #[must_use]
+    pub const fn with_gas_estimate(mut self, gas_estimate: u64) -> Self {
+        self.gas_estimate = Some(gas_estimate);
+        self
+    }
+
+    /// Set the effective gas price (in wei).
+    #[must_use]
+    pub const fn with_effective_gas_price(mut self, price: u128) -> Self {
+        self.effective_gas_price = Some(price);
+        self
+    }
+
+    /// Set the block the transaction was included in.
+    #[must_use]
+    pub const fn with_block_number(mut self, block_number: u64) -> Self {
+        self.block_number = Some(block_number);
+        self
+    }
+
+    /// Set the execution duration.
+    #[must_use]
+    pub fn with_duration(mut self, duration: std::time::Duration) -> Self {
+        self.duration_ms = Some(u64::try_from(duration.as_millis()).unwrap_or(u64::MAX));
+        self
+    }
+
+    /// Set the plugin-specific payload.
+    #[must_use]
+    pub fn with_detail(mut self, detail: serde_json::Value) -> Self {
+        self.detail = detail;
+        self
+    }
+
+    /// Set the plugin state derived from the receipt.
+    #[must_use]
+    pub fn with_plugin_state(mut self, state: serde_json::Value) -> Self {
+        self.plugin_state = Some(state);
+        self
+    }
+
+    /// Set the tokens the transaction moved.
+    #[must_use]
+    pub fn with_data_flows(mut self, flows: Vec<DataFlow>) -> Self {
+        self.data_flows = flows;
+        self
+    }
+
+    /// Set the error.
+    #[must_use]
+    pub fn with_error(mut self, error: ActionError) -> Self {
+        self.error = Some(error);
+        self
+    }
+
+    /// Record how a transaction that was not mined in time was settled.
+    #[must_use]
+    pub const fn with_replacement(mut self, outcome: ReplacementOutcome, attempts: u32) -> Self {
+        self.replacement = Some(Replacement { outcome, attempts });
+        self
+    }
+
+    /// Record how the engine submitted the transaction.
+    #[must_use]
+    pub const fn with_submission(mut self, submission: Submission) -> Self {
+        self.submission = Some(submission);
+        self
+    }
+
+    /// Check whether the action succeeded on chain.
+    #[must_use]
+    pub const fn is_success(&self) -> bool {
+        self.status.is_success()
+    }
+
+    /// Check whether the action failed with an error worth retrying.
+    #[must_use]
+    pub fn is_retryable(&self) -> bool {
+        self.status.is_failure() && self.error.as_ref().is_some_and(|e| e.retryable)
+    }
+
+    /// Total gas cost in wei, if both gas used and price are known.
+    #[must_use]
+    pub fn gas_cost_wei(&self) -> Option<u128> {
+        Some(u128::from(self.gas_used?).saturating_mul(self.effective_gas_price?))
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// ACTION REQUIREMENTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// What a wallet needs before an action can apply at all.
+///
+/// Declared per action by [`ActionPlugin::requirements`], and checked
+/// against the wallet's cached state, so a plugin that cannot act is skipped
+/// without deciding. Requirements are lower bounds: meeting them does not
+/// mean the plugin will act.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ActionRequirements {
+    /// Minimum native balance in wei, e.g. to pay for gas.
+    pub min_native_balance: U256,
+
+    /// Minimum balance of each token, by token address, in the token's
+    /// smallest unit.
+    pub min_token_balances: HashMap<Address, U256>,
+
+    /// Whether the wallet must already hold a position.
+    ///
+    /// Positions are plugin-specific, so only the plugin can check this.
+    pub requires_position: bool,
+}
+
+impl ActionRequirements {
+    /// Requirements any wallet meets.
+    #[must_use]
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    /// Require at least `min` wei of the native token.
+    #[must_use]
+    pub const fn with_native_balance(mut self, min: U256) -> Self {
+        self.min_native_balance = min;
+        self
+    }
+
+    /// Require at least `min` of `token`.
+    #[must_use]
+    pub fn with_token_balance(mut self, token: Address, min: U256) -> Self {
+        self.min_token_balances.insert(token, min);
+        self
+    }
+
+    /// Require an existing position.
+    #[must_use]
+    pub const fn with_position(mut self) -> Self {
+        self.requires_position = true;
+        self
+    }
+
+    /// Check if the wallet's cached balances meet the balance requirements.
+    ///
+    /// Untracked tokens count as a zero balance.
+    #[must_use]
+    pub fn balances_met_by(&self, wallet: &WalletState) -> bool {
+        wallet.native_balance >= self.min_native_balance
+            && self
+                .min_token_balances
+                .iter()
+                .all(|(token, min)| wallet.token_balance(*token) >= *min)
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// PLUGIN ACTIVITY
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// What a wallet has done with a plugin so far, for activity reports.
+///
+/// Amounts are cumulative, in the plugin's token's smallest unit; a report
+/// covering a day takes the difference of two readings. See
+/// [`ActionPlugin::activity`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PluginActivity {
+    /// Put at stake.
+    pub staked: U256,
+
+    /// Taken back out, including winnings taken out with it.
+    pub extracted: U256,
+
+    /// Won from resolved bets.
+    pub won: U256,
+
+    /// Lost to resolved bets and stakes that were wiped out.
+    pub lost: U256,
+
+    /// Positions the wallet holds now, one short description each.
+    pub positions: Vec<String>,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// DATA FLOWS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Tokens moving in or out of a wallet, for its profit and loss.
+///
+/// Amounts are in the plugin's token's smallest unit. Stakes and what is
+/// taken back out of them are told apart from other flows, so that proceeds
+/// can be matched against the stakes they came from. See
+/// [`ActionPlugin::data_flows`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DataFlow {
+    /// Put into the wallet's position.
+    Staked {
+        /// Amount staked.
+        amount: U256,
+        /// Whether the stake opened a new position, rather than adding to
+        /// the open one.
+        opened: bool,
+    },
+
+    /// Taken out of the wallet's position.
+    Exited {
+        /// Stake taken out, `None` for all of it.
+        principal: Option<U256>,
+        /// Paid out for it, rewards included and tolls deducted.
+        proceeds: U256,
+    },
+
+    /// Wagered on a bet or game.
+    Wagered {
+        /// Amount wagered.
+        amount: U256,
+    },
+
+    /// Paid to the wallet outside its position: winnings, refunds, claimed
+    /// rewards.
+    Received {
+        /// Amount received.
+        amount: U256,
+    },
+}
+
+impl DataFlow {
+    /// What the wallet paid out.
+    #[must_use]
+    pub const fn outflow(&self) -> U256 {
+        match self {
+            Self::Staked { amount, .. } | Self::Wagered { amount } => *amount,
+            Self::Exited { .. } | Self::Received { .. } => U256::ZERO,
+        }
+    }
+
+    /// What the wallet received.
+    #[must_use]
+    pub const fn inflow(&self) -> U256 {
+        match self {
+            Self::Exited { proceeds, .. } => *proceeds,
+            Self::Received { amount } => *amount,
+            Self::Staked { .. } | Self::Wagered { .. } => U256::ZERO,
         }
     }
 }
@@ -172,6 +754,9 @@ pub struct PluginContext<'a> {
 
     /// Plugin-specific configuration (from config file).
     pub config: &'a serde_json::Value,
+
+    /// Cooldowns of the wallet being decided for.
+    pub cooldowns: ActionCooldowns,
 }
 
 impl std::fmt::Debug for PluginContext<'_> {
@@ -180,6 +765,7 @@ impl std::fmt::Debug for PluginContext<'_> {
             .field("now", &self.now)
             .field("rng", &"<RngCore + Send + Sync>")
             .field("config", &self.config)
+            .field("cooldowns", &self.cooldowns)
             .finish()
     }
 }
@@ -192,7 +778,28 @@ impl<'a> PluginContext<'a> {
         rng: &'a mut (dyn rand::RngCore + Send + Sync),
         config: &'a serde_json::Value,
     ) -> Self {
-        Self { now, rng, config }
+        Self {
+            now,
+            rng,
+            config,
+            cooldowns: ActionCooldowns::new(),
+        }
+    }
+
+    /// Set the cooldowns of the wallet being decided for.
+    #[must_use]
+    pub fn with_cooldowns(mut self, cooldowns: ActionCooldowns) -> Self {
+        self.cooldowns = cooldowns;
+        self
+    }
+
+    /// Check whether an action was executed too recently to be chosen again.
+    ///
+    /// Plugins should skip candidate actions for which this returns `true`;
+    /// the orchestrator rejects them regardless.
+    #[must_use]
+    pub fn is_on_cooldown(&self, action_id: &str) -> bool {
+        self.cooldowns.is_on_cooldown(action_id, self.now)
     }
 }
 
@@ -244,8 +851,102 @@ pub trait ActionPlugin: Send + Sync + Debug {
     fn name(&self) -> &str;
 
     /// List of actions this plugin can perform.
+    ///
+    /// Each must be a valid [`ActionId`] starting with the plugin's own
+    /// [`id`](Self::id), or the [`PluginRegistry`](super::PluginRegistry)
+    /// refuses the plugin.
     fn available_actions(&self) -> Vec<ActionId>;
 
+    /// Minimum time between two executions of an action by the same wallet.
+    ///
+    /// Profiles can override this per action via
+    /// [`BehaviorProfile::action_cooldown_secs`]. Default: no cooldown.
+    fn default_cooldown(&self, _action: &ActionId) -> Option<chrono::Duration> {
+        None
+    }
+
+    /// What executing an action is expected to spend.
+    ///
+    /// Checked against the wallet's and the fleet's budgets before the action
+    /// runs (see [`BudgetManager`](crate::safety::BudgetManager)). Default:
+    /// one action that uses no gas or DATA.
+    fn estimate_spend(&self, _action: &Action) -> Spend {
+        Spend::action()
+    }
+
+    /// What a wallet needs before `action` can apply at all.
+    ///
+    /// Default: [no requirements](ActionRequirements::none).
+    fn requirements(&self, _action: &ActionId) -> ActionRequirements {
+        ActionRequirements::none()
+    }
+
+    /// Cheap check whether this plugin could act for the wallet at `now`.
+    ///
+    /// Called by the engine before [`Self::decide_action`], which is skipped
+    /// if this returns `false`. Must not make RPC calls; it exists to save
+    /// them. Returning `true` is always safe.
+    ///
+    /// Default: whether the cached balances meet the
+    /// [requirements](Self::requirements) of any available action. Position
+    /// requirements are left to plugins overriding this.
+    fn can_possibly_act(&self, wallet: &WalletState, _now: chrono::DateTime<chrono::Utc>) -> bool {
+        self.available_actions()
+            .iter()
+            .any(|action| self.requirements(action).balances_met_by(wallet))
+    }
+
+    /// Check whether an action only winds a wallet down: leaves a position
+    /// or takes out what it earned, and puts nothing new at stake.
+    ///
+    /// Retiring wallets only run exit actions, see
+    /// [`Retirement`](crate::wallet::Retirement). Default: no action is one.
+    fn is_exit_action(&self, _action: &ActionId) -> bool {
+        false
+    }
+
+    /// Check whether the wallet still has positions or bets open with this
+    /// plugin, from its cached state.
+    ///
+    /// A retiring wallet is retired only once no plugin holds anything open
+    /// for it. Must not make RPC calls. Default: nothing is ever open.
+    fn has_open_positions(&self, _wallet: &WalletState) -> bool {
+        false
+    }
+
+    /// What the wallet has done with this plugin so far, from its cached
+    /// state.
+    ///
+    /// Read for activity reports. Must not make RPC calls. Default: `None`,
+    /// the plugin moves no tokens worth reporting.
+    fn activity(&self, _wallet: &WalletState) -> Option<PluginActivity> {
+        None
+    }
+
+    /// Tokens an action's transaction moved, from its receipt.
+    ///
+    /// Called by the engine once the transaction is mined without
+    /// reverting; the flows are recorded in the fleet's profit and loss
+    /// ledger. Default: none.
+    fn data_flows(
+        &self,
+        _action: &Action,
+        _wallet: &WalletState,
+        _receipt: &evm_provider::TransactionReceipt,
+    ) -> Vec<DataFlow> {
+        Vec::new()
+    }
+
+    /// Tokens paid to the wallet outside its own transactions, such as the
+    /// winnings of bets resolved since, by an ID that never changes.
+    ///
+    /// Read after each decision for the wallet, from the plugin's cached
+    /// state; the fleet records each ID once, so a flow can be returned on
+    /// every call. Must not make RPC calls. Default: none.
+    fn settled_flows(&self, _wallet: &WalletState) -> Vec<(String, DataFlow)> {
+        Vec::new()
+    }
+
     /// Decide what action (if any) this plugin wants to take.
     ///
     /// Called by the behavior engine. The plugin examines the wallet state
@@ -283,6 +984,12 @@ pub trait ActionPlugin: Send + Sync + Debug {
     /// # Returns
     ///
     /// Result containing success/failure info and transaction hash.
+    ///
+    /// # Errors
+    ///
+    /// The [`ErrorClass`](crate::error::ErrorClass) of a returned error decides
+    /// whether the orchestrator retries the action in place, backs off, or
+    /// counts it toward the wallet's circuit breaker.
     async fn execute_action(
         &self,
         action: &Action,
@@ -290,6 +997,27 @@ pub trait ActionPlugin: Send + Sync + Debug {
         nonce: u64,
     ) -> Result<ActionResult>;
 
+    /// Build a replacement for a transaction that was not mined in time.
+    ///
+    /// Called by the engine once the action's receipt timeout passes. The
+    /// replacement must be signed with `original.nonce` and pay `bump_pct`
+    /// percent more in fees than the original; it is sent as is.
+    ///
+    /// Return `None` to decline, and the engine cancels the transaction
+    /// with a self-transfer instead. Default: decline.
+    ///
+    /// # Errors
+    ///
+    /// A returned error is logged and handled like declining.
+    async fn build_replacement(
+        &self,
+        _action: &Action,
+        _original: &PendingTx,
+        _bump_pct: u32,
+    ) -> Result<Option<Bytes>> {
+        Ok(None)
+    }
+
     /// Read current state relevant to this plugin.
     ///
     /// Called to refresh wallet state with plugin-specific data. The returned
@@ -304,6 +1032,47 @@ pub trait ActionPlugin: Send + Sync + Debug {
     /// Plugin-specific state as JSON.
     async fn read_state(&self, address: Address) -> Result<serde_json::Value>;
 
+    /// Read current state relevant to this plugin as `T`.
+    ///
+    /// Converts what [`Self::read_state`] returns the way stored state is
+    /// read, see [`decode_plugin_state`](crate::wallet::decode_plugin_state).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the state cannot be read, or cannot be read as
+    /// `T`.
+    async fn read_state_typed<T: PluginState + Send>(&self, address: Address) -> Result<T>
+    where
+        Self: Sized,
+    {
+        let state = self.read_state(address).await?;
+        decode_plugin_state(self.id(), self.state_schema_version(), state)
+    }
+
+    /// Schema version of the state returned by [`Self::read_state`].
+    ///
+    /// Stored with the state, see [`PluginState`](crate::wallet::PluginState).
+    /// Default: 1.
+    fn state_schema_version(&self) -> u32 {
+        1
+    }
+
+    /// Derive the wallet's state from the receipt of one of its actions.
+    ///
+    /// Called by the engine once the action's transaction is mined. A
+    /// returned state is stored like one from [`Self::read_state`] and
+    /// replaces the next read, saving the RPC calls when the receipt's events
+    /// already say what changed. Return `None` to read the state as usual.
+    /// Default: `None`.
+    fn state_from_receipt(
+        &self,
+        _action: &Action,
+        _wallet: &WalletState,
+        _receipt: &evm_provider::TransactionReceipt,
+    ) -> Option<serde_json::Value> {
+        None
+    }
+
     /// Build transaction data for an action (optional).
     ///
     /// If implemented, returns the raw transaction data that would be sent.
@@ -316,8 +1085,116 @@ pub trait ActionPlugin: Send + Sync + Debug {
         _wallet: &WalletState,
         _nonce: u64,
     ) -> Result<Bytes> {
-        Err(crate::error::FleetError::PluginExecution(
-            "build_transaction not implemented".into(),
+        Err(crate::error::FleetError::plugin(
+            crate::error::ErrorClass::Configuration,
+            "build_transaction not implemented",
         ))
     }
 }
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+#[allow(clippy::expect_used)]
+mod tests {
+    use alloy::primitives::B256;
+
+    use super::*;
+
+    fn receipt(success: bool) -> evm_provider::TransactionReceipt {
+        evm_provider::TransactionReceipt {
+            tx_hash: TxHash::repeat_byte(1),
+            block_hash: B256::ZERO,
+            block_number: 42,
+            tx_index: 0,
+            from: Address::ZERO,
+            to: None,
+            contract_address: None,
+            gas_used: 21_000,
+            success,
+            logs: vec![],
+        }
+    }
+
+    #[test]
+    fn result_from_receipt() {
+        let result = ActionResult::from_receipt(&receipt(true));
+        assert_eq!(result.status, ActionStatus::Succeeded);
+        assert_eq!(result.tx_hash, Some(TxHash::repeat_byte(1)));
+        assert_eq!((result.gas_used, result.block_number), (Some(21_000), Some(42)));
+        assert!(result.error.is_none());
+
+        let result = ActionResult::from_receipt(&receipt(false));
+        assert_eq!(result.status, ActionStatus::Reverted);
+        assert!(!result.is_retryable());
+    }
+
+    #[test]
+    fn failure_categories() {
+        let failure = ActionResult::failure("boom");
+        assert_eq!(failure.status, ActionStatus::Dropped);
+        assert_eq!(failure.error, Some(ActionError::permanent("boom")));
+
+        let dropped = ActionResult::dropped(TxHash::ZERO, "not mined");
+        assert!(dropped.is_retryable());
+
+        // Skipped and simulated actions are neither successes nor failures
+        for result in [ActionResult::skipped("no position"), ActionResult::simulated()] {
+            assert!(!result.is_success());
+            assert!(!result.status.is_failure());
+        }
+        assert_eq!(ActionResult::skipped("no position").detail["reason"], "no position");
+    }
+
+    #[test]
+    fn result_round_trips_through_json() {
+        let result = ActionResult::success_with_gas(TxHash::ZERO, 50_000)
+            .with_effective_gas_price(7)
+            .with_duration(std::time::Duration::from_millis(1500))
+            .with_detail(serde_json::json!({ "amount": "100" }));
+
+        let json = serde_json::to_value(&result).expect("should serialize");
+        assert_eq!(json["status"], "succeeded");
+        assert_eq!(json["duration_ms"], 1500);
+
+        let back: ActionResult = serde_json::from_value(json).expect("should deserialize");
+        assert_eq!(back, result);
+        assert_eq!(back.gas_cost_wei(), Some(350_000));
+    }
+
+    #[test]
+    fn replacement_outcome_is_serialized_when_present() {
+        let plain = serde_json::to_value(ActionResult::success(TxHash::ZERO)).expect("serialize");
+        assert!(plain.get("replacement").is_none());
+
+        let cancelled = ActionResult::dropped(TxHash::ZERO, "cancelled")
+            .with_replacement(ReplacementOutcome::Cancelled, 2);
+        let json = serde_json::to_value(&cancelled).expect("should serialize");
+        assert_eq!(json["replacement"]["outcome"], "cancelled");
+        assert_eq!(json["replacement"]["attempts"], 2);
+
+        let back: ActionResult = serde_json::from_value(json).expect("should deserialize");
+        assert_eq!(back, cancelled);
+        assert!(ReplacementOutcome::Cancelled.used_nonce());
+        assert!(!ReplacementOutcome::Abandoned.used_nonce());
+    }
+
+    #[test]
+    fn submission_is_serialized_when_present() {
+        let plain = serde_json::to_value(ActionResult::success(TxHash::ZERO)).expect("serialize");
+        assert!(plain.get("submission").is_none());
+
+        let realtime = ActionResult::success(TxHash::ZERO).with_submission(Submission {
+            path: SubmissionPath::Realtime,
+            receipt_latency_ms: Some(12),
+        });
+        let json = serde_json::to_value(&realtime).expect("should serialize");
+        assert_eq!(json["submission"]["path"], "realtime");
+        assert_eq!(json["submission"]["receipt_latency_ms"], 12);
+
+        let back: ActionResult = serde_json::from_value(json).expect("should deserialize");
+        assert_eq!(back, realtime);
+    }
+}
@@ -0,0 +1,143 @@
+//! Named behavior profiles of a fleet.
+//!
+//! Wallets refer to their profile by name. The [`ProfileCatalog`] maps those
+//! names to concrete [`BehaviorProfile`]s, resolved at startup (e.g. from
+//! presets and config overrides), so the service and plugins never look at
+//! unresolved configuration.
+
+use std::collections::HashMap;
+
+use super::BehaviorProfile;
+
+/// Resolved behavior profiles by name.
+///
+/// # Example
+///
+/// ```
+/// use fleet_core::profiles::{BehaviorProfile, ProfileCatalog};
+///
+/// let mut catalog = ProfileCatalog::new();
+/// let mut patient = BehaviorProfile::whale();
+/// patient.name = "patient_whale".into();
+/// patient.patience = 1.0;
+/// catalog.insert(patient);
+///
+/// assert!(catalog.get("patient_whale").is_some_and(|p| p.patience == 1.0));
+/// assert!(catalog.get("whale").is_none());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProfileCatalog {
+    /// Profiles by their name.
+    profiles: HashMap<String, BehaviorProfile>,
+}
+
+impl ProfileCatalog {
+    /// Create an empty catalog.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a catalog of the preset profiles, see
+    /// [`BehaviorProfile::preset`].
+    #[must_use]
+    pub fn presets() -> Self {
+        BehaviorProfile::PRESETS
+            .into_iter()
+            .filter_map(BehaviorProfile::preset)
+            .collect()
+    }
+
+    /// Add a profile under its name, returning the profile it replaces.
+    pub fn insert(&mut self, profile: BehaviorProfile) -> Option<BehaviorProfile> {
+        self.profiles.insert(profile.name.clone(), profile)
+    }
+
+    /// Get a profile by name.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&BehaviorProfile> {
+        self.profiles.get(name)
+    }
+
+    /// Check whether a profile is defined.
+    #[must_use]
+    pub fn contains(&self, name: &str) -> bool {
+        self.profiles.contains_key(name)
+    }
+
+    /// Get a wallet's variation of the named profile, see
+    /// [`BehaviorProfile::personalize`].
+    #[must_use]
+    pub fn personalized(&self, name: &str, seed: u64) -> Option<BehaviorProfile> {
+        self.get(name)
+            .map(|base| BehaviorProfile::personalize(base, seed))
+    }
+
+    /// Names of all profiles, in no particular order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.profiles.keys().map(String::as_str)
+    }
+
+    /// Number of profiles.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.profiles.len()
+    }
+
+    /// Check whether the catalog is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.profiles.is_empty()
+    }
+}
+
+impl FromIterator<BehaviorProfile> for ProfileCatalog {
+    fn from_iter<I: IntoIterator<Item = BehaviorProfile>>(iter: I) -> Self {
+        let mut catalog = Self::new();
+        for profile in iter {
+            catalog.insert(profile);
+        }
+        catalog
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn presets_are_listed_by_name() {
+        let catalog = ProfileCatalog::presets();
+
+        assert_eq!(catalog.len(), BehaviorProfile::PRESETS.len());
+        assert_eq!(catalog.get("degen"), Some(&BehaviorProfile::degen()));
+        assert!(!catalog.contains("custom"));
+    }
+
+    #[test]
+    fn later_profiles_replace_earlier_ones() {
+        let mut custom = BehaviorProfile::grinder();
+        custom.activity_level = 1.0;
+        let mut catalog = ProfileCatalog::presets();
+
+        let replaced = catalog.insert(custom.clone());
+        assert_eq!(replaced, Some(BehaviorProfile::grinder()));
+        assert_eq!(catalog.get("grinder"), Some(&custom));
+    }
+
+    #[test]
+    fn personalizes_by_name() {
+        let catalog = ProfileCatalog::presets();
+        let base = BehaviorProfile::casual();
+
+        assert_eq!(
+            catalog.personalized("casual", 7),
+            Some(BehaviorProfile::personalize(&base, 7))
+        );
+        assert!(catalog.personalized("custom", 7).is_none());
+    }
+}
@@ -0,0 +1,765 @@
+//! Fleet-level diversity of personalized profiles.
+//!
+//! [Personalization](BehaviorProfile::personalize) varies each wallet around
+//! its base profile, but a fleet made mostly of one profile still crowds into
+//! the same active hours, the same risk band and, with integer rounding,
+//! often identical intervals. A [`FleetComposer`] looks at the personalized
+//! profiles of the whole fleet at startup and adjusts them until together
+//! they meet a set of [`DiversityTargets`], reporting the distribution it
+//! reached in a [`CompositionReport`].
+//!
+//! Every adjustment is derived from the fleet seed and the wallet ID, so the
+//! same fleet always gets the same composition, and adding a wallet does not
+//! reshuffle the others.
+//!
+//! # Example
+//!
+//! ```
+//! use fleet_core::profiles::{BehaviorProfile, DiversityTargets, FleetComposer};
+//!
+//! let targets = DiversityTargets {
+//!     min_hourly_coverage: 0.25,
+//!     ..DiversityTargets::default()
+//! };
+//! let fleet = (0..8).map(|i| (format!("w{i}"), BehaviorProfile::whale()));
+//! let composition = FleetComposer::new(targets, 7).compose(fleet);
+//!
+//! // Whales are only active 14-22; some now cover the rest of the day
+//! assert!(composition.report().is_satisfied());
+//! assert!(composition.report().hourly_coverage.iter().all(|&wallets| wallets >= 2));
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+
+use alloy::primitives::keccak256;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use super::BehaviorProfile;
+
+/// Interval parameters that make two wallets tick alike: action interval,
+/// jitter and burst spacing.
+type IntervalKey = (u64, u8, u64);
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TARGETS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Distribution a fleet's profiles should have together.
+///
+/// The default sets no target, leaving profiles as they are.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiversityTargets {
+    /// Share of wallets (0.0-1.0) that must be active in every hour of the
+    /// day.
+    pub min_hourly_coverage: f64,
+
+    /// Target share of wallets per `risk_tolerance` bucket.
+    ///
+    /// The buckets split 0.0-1.0 into equal widths, lowest first, and the
+    /// shares are taken relative to their sum. Empty for no target.
+    pub risk_buckets: Vec<f64>,
+
+    /// Largest share of wallets (0.0-1.0) with identical interval
+    /// parameters. At least one wallet may always have its own.
+    pub max_shared_interval_fraction: f64,
+}
+
+impl Default for DiversityTargets {
+    fn default() -> Self {
+        Self {
+            min_hourly_coverage: 0.0,
+            risk_buckets: Vec::new(),
+            max_shared_interval_fraction: 1.0,
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// COMPOSER
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Adjusts the personalized profiles of a fleet to meet
+/// [`DiversityTargets`].
+///
+/// Targets are met in turn, each leaving the earlier ones intact:
+///
+/// 1. **Risk**: wallets are ranked by `risk_tolerance` and the ranks split
+///    into the bucket quotas, so the most risk-averse wallets keep the lowest
+///    buckets. A wallet outside its bucket gets a seeded value inside it.
+/// 2. **Intervals**: of wallets sharing interval parameters beyond the
+///    limit, the extra ones get their action interval nudged by a few
+///    seconds to an unused value.
+/// 3. **Hours**: while an hour is covered by too few wallets, a wallet
+///    shifts its active window the shortest way to include it, provided
+///    no hour it leaves falls below the target.
+///
+/// Adjusted profiles stay [valid](BehaviorProfile::validate) if the input
+/// was.
+#[derive(Debug, Clone)]
+pub struct FleetComposer {
+    /// Distribution to reach.
+    targets: DiversityTargets,
+    /// Fleet seed all adjustments derive from.
+    seed: u64,
+}
+
+impl FleetComposer {
+    /// Create a composer for `targets`, seeded by the fleet `seed`.
+    #[must_use]
+    pub const fn new(targets: DiversityTargets, seed: u64) -> Self {
+        Self { targets, seed }
+    }
+
+    /// Compose the personalized profiles of a fleet, keyed by wallet ID.
+    #[must_use]
+    pub fn compose(
+        &self,
+        profiles: impl IntoIterator<Item = (String, BehaviorProfile)>,
+    ) -> Composition {
+        let profiles: BTreeMap<_, _> = profiles.into_iter().collect();
+        let original = profiles.clone();
+        let mut fleet: Vec<Member> = profiles
+            .into_iter()
+            .map(|(wallet_id, profile)| Member {
+                key: self.wallet_key(&wallet_id),
+                wallet_id,
+                profile,
+            })
+            .collect();
+        // Wallets take turns in seeded order, independent of the others
+        fleet.sort_by(|a, b| {
+            a.key
+                .cmp(&b.key)
+                .then_with(|| a.wallet_id.cmp(&b.wallet_id))
+        });
+
+        let risk_quotas = self.compose_risk(&mut fleet);
+        let allowed_shared_intervals = self.compose_intervals(&mut fleet);
+        let required_hourly_coverage = self.compose_hours(&mut fleet);
+
+        let profiles: BTreeMap<_, _> = fleet
+            .into_iter()
+            .map(|member| (member.wallet_id, member.profile))
+            .collect();
+        let adjusted_wallets = profiles
+            .iter()
+            .filter(|(id, profile)| original.get(*id) != Some(profile))
+            .count();
+        let report = CompositionReport {
+            wallets: profiles.len(),
+            adjusted_wallets,
+            hourly_coverage: hourly_coverage(profiles.values()).to_vec(),
+            required_hourly_coverage,
+            risk_histogram: risk_histogram(profiles.values(), risk_quotas.len()),
+            risk_quotas,
+            max_shared_intervals: interval_groups(profiles.values())
+                .into_values()
+                .max()
+                .unwrap_or(0),
+            allowed_shared_intervals,
+        };
+        Composition { profiles, report }
+    }
+
+    /// Seeded ordering key of a wallet.
+    fn wallet_key(&self, wallet_id: &str) -> u64 {
+        let mut input = self.seed.to_be_bytes().to_vec();
+        input.extend_from_slice(wallet_id.as_bytes());
+        let hash = keccak256(input);
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(&hash[..8]);
+        u64::from_be_bytes(bytes)
+    }
+
+    /// Move wallets into their risk buckets, returning the bucket quotas.
+    fn compose_risk(&self, fleet: &mut [Member]) -> Vec<usize> {
+        let quotas = quotas(&self.targets.risk_buckets, fleet.len());
+        if quotas.is_empty() {
+            return quotas;
+        }
+        let buckets = quotas.len();
+
+        let mut ranked: Vec<_> = (0..fleet.len()).collect();
+        ranked.sort_by(|&a, &b| {
+            fleet[a]
+                .profile
+                .risk_tolerance
+                .total_cmp(&fleet[b].profile.risk_tolerance)
+                .then_with(|| fleet[a].key.cmp(&fleet[b].key))
+        });
+        let assigned = quotas
+            .iter()
+            .enumerate()
+            .flat_map(|(bucket, &quota)| std::iter::repeat_n(bucket, quota));
+        for (index, bucket) in ranked.into_iter().zip(assigned) {
+            let member = &mut fleet[index];
+            if risk_bucket(member.profile.risk_tolerance, buckets) != bucket {
+                let offset = member.rng(1).random_range(0.05..0.95);
+                #[allow(clippy::cast_precision_loss)] // Bucket counts are small
+                let risk = (bucket as f64 + offset) / buckets as f64;
+                member.profile.risk_tolerance = risk;
+            }
+        }
+        quotas
+    }
+
+    /// Nudge the intervals of wallets sharing them beyond the limit,
+    /// returning the limit.
+    fn compose_intervals(&self, fleet: &mut [Member]) -> usize {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)] // Share of the fleet size
+        #[allow(clippy::cast_precision_loss)] // Fleet sizes are small
+        let allowed = ((self.targets.max_shared_interval_fraction.clamp(0.0, 1.0)
+            * fleet.len() as f64)
+            .floor() as usize)
+            .max(1);
+
+        let mut groups = interval_groups(fleet.iter().map(|m| &m.profile));
+        let mut kept: HashMap<IntervalKey, usize> = HashMap::new();
+        for member in fleet.iter_mut() {
+            let key = interval_key(&member.profile);
+            let seen = kept.entry(key).or_default();
+            if *seen < allowed {
+                *seen += 1;
+                continue;
+            }
+
+            let nudged = nudge_interval(&member.profile, &mut member.rng(2), |candidate| {
+                groups.get(&candidate).copied().unwrap_or(0) < allowed
+            });
+            member.profile.action_interval_secs = nudged;
+            if let Some(count) = groups.get_mut(&key) {
+                *count -= 1;
+            }
+            let new_key = interval_key(&member.profile);
+            *groups.entry(new_key).or_default() += 1;
+            *kept.entry(new_key).or_default() += 1;
+        }
+        allowed
+    }
+
+    /// Shift active windows until every hour is covered by enough wallets,
+    /// returning the wallets each hour needs.
+    fn compose_hours(&self, fleet: &mut [Member]) -> usize {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)] // Share of the fleet size
+        #[allow(clippy::cast_precision_loss)] // Fleet sizes are small
+        let required =
+            (self.targets.min_hourly_coverage.clamp(0.0, 1.0) * fleet.len() as f64).ceil() as usize;
+        let mut coverage = hourly_coverage(fleet.iter().map(|m| &m.profile));
+
+        // Every shift lowers the total shortfall, so this ends
+        loop {
+            let mut progress = false;
+            for hour in 0..24 {
+                if coverage[usize::from(hour)] >= required {
+                    continue;
+                }
+                let shifted = fleet.iter_mut().find_map(|member| {
+                    let profile = &member.profile;
+                    if profile.is_active_hour(hour) || !has_valid_hours(profile) {
+                        return None;
+                    }
+                    // Hours the wallet leaves must keep enough wallets
+                    let candidate = shifts_to_cover(profile, hour)
+                        .into_iter()
+                        .map(|shift| shifted_window(profile, shift))
+                        .find(|candidate| {
+                            (0..24).all(|h| {
+                                !profile.is_active_hour(h)
+                                    || candidate.is_active_hour(h)
+                                    || coverage[usize::from(h)] > required
+                            })
+                        })?;
+                    Some((member, candidate))
+                });
+                let Some((member, candidate)) = shifted else {
+                    continue;
+                };
+                for h in 0..24 {
+                    let slot = &mut coverage[usize::from(h)];
+                    match (
+                        member.profile.is_active_hour(h),
+                        candidate.is_active_hour(h),
+                    ) {
+                        (true, false) => *slot -= 1,
+                        (false, true) => *slot += 1,
+                        _ => {}
+                    }
+                }
+                member.profile = candidate;
+                progress = true;
+            }
+            if !progress {
+                break;
+            }
+        }
+        required
+    }
+}
+
+/// A wallet being composed.
+#[derive(Debug)]
+struct Member {
+    /// Seeded ordering key, see [`FleetComposer::wallet_key`].
+    key: u64,
+    /// Wallet ID.
+    wallet_id: String,
+    /// Profile being adjusted.
+    profile: BehaviorProfile,
+}
+
+impl Member {
+    /// Random stream of one composition step of this wallet.
+    fn rng(&self, step: u64) -> StdRng {
+        StdRng::seed_from_u64(self.key ^ step.rotate_right(8))
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// COMPOSITION
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Composed profiles of a fleet, see [`FleetComposer::compose`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Composition {
+    /// Profiles by wallet ID.
+    profiles: BTreeMap<String, BehaviorProfile>,
+    /// Distribution reached.
+    report: CompositionReport,
+}
+
+impl Composition {
+    /// Get the composed profile of a wallet.
+    #[must_use]
+    pub fn get(&self, wallet_id: &str) -> Option<&BehaviorProfile> {
+        self.profiles.get(wallet_id)
+    }
+
+    /// Composed profiles by wallet ID.
+    pub fn profiles(&self) -> impl Iterator<Item = (&str, &BehaviorProfile)> {
+        self.profiles
+            .iter()
+            .map(|(id, profile)| (id.as_str(), profile))
+    }
+
+    /// Distribution the composition reached.
+    #[must_use]
+    pub const fn report(&self) -> &CompositionReport {
+        &self.report
+    }
+}
+
+/// Distribution of a composed fleet.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompositionReport {
+    /// Wallets composed.
+    pub wallets: usize,
+
+    /// Wallets whose profile had to be adjusted.
+    pub adjusted_wallets: usize,
+
+    /// Wallets active in each UTC hour of the day, midnight first.
+    pub hourly_coverage: Vec<usize>,
+
+    /// Wallets every hour should be covered by.
+    pub required_hourly_coverage: usize,
+
+    /// Wallets per `risk_tolerance` bucket, empty without a risk target.
+    pub risk_histogram: Vec<usize>,
+
+    /// Wallets each risk bucket should hold.
+    pub risk_quotas: Vec<usize>,
+
+    /// Most wallets sharing identical interval parameters.
+    pub max_shared_intervals: usize,
+
+    /// Most wallets allowed to share identical interval parameters.
+    pub allowed_shared_intervals: usize,
+}
+
+impl CompositionReport {
+    /// Check whether every hour is covered by enough wallets.
+    #[must_use]
+    pub fn coverage_met(&self) -> bool {
+        self.hourly_coverage
+            .iter()
+            .all(|&wallets| wallets >= self.required_hourly_coverage)
+    }
+
+    /// Check whether the risk buckets hold their quotas.
+    #[must_use]
+    pub fn risk_met(&self) -> bool {
+        self.risk_histogram == self.risk_quotas
+    }
+
+    /// Check whether few enough wallets share interval parameters.
+    #[must_use]
+    pub const fn intervals_met(&self) -> bool {
+        self.max_shared_intervals <= self.allowed_shared_intervals
+    }
+
+    /// Check whether every target was met.
+    #[must_use]
+    pub fn is_satisfied(&self) -> bool {
+        self.coverage_met() && self.risk_met() && self.intervals_met()
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// HELPERS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Split `count` wallets by `shares`, largest remainders first.
+///
+/// Empty if there are no shares or they do not add up to anything.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)] // Shares of `count`
+#[allow(clippy::cast_precision_loss)] // Fleet sizes are small
+fn quotas(shares: &[f64], count: usize) -> Vec<usize> {
+    let total: f64 = shares.iter().map(|share| share.max(0.0)).sum();
+    if total <= 0.0 {
+        return Vec::new();
+    }
+    let exact: Vec<f64> = shares
+        .iter()
+        .map(|share| share.max(0.0) / total * count as f64)
+        .collect();
+    let mut quotas: Vec<usize> = exact.iter().map(|e| e.floor() as usize).collect();
+
+    let mut by_remainder: Vec<_> = (0..exact.len()).collect();
+    by_remainder.sort_by(|&a, &b| {
+        let remainder = |i: usize| exact[i] - exact[i].floor();
+        remainder(b).total_cmp(&remainder(a)).then(a.cmp(&b))
+    });
+    let missing = count.saturating_sub(quotas.iter().sum());
+    for bucket in by_remainder.into_iter().take(missing) {
+        quotas[bucket] += 1;
+    }
+    quotas
+}
+
+/// Bucket of a risk tolerance among `buckets` equal-width buckets.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)] // Clamped to 0.0-1.0
+#[allow(clippy::cast_precision_loss)] // Bucket counts are small
+fn risk_bucket(risk: f64, buckets: usize) -> usize {
+    ((risk.clamp(0.0, 1.0) * buckets as f64) as usize).min(buckets - 1)
+}
+
+/// Wallets per risk bucket, empty for no buckets.
+fn risk_histogram<'a>(
+    profiles: impl Iterator<Item = &'a BehaviorProfile>,
+    buckets: usize,
+) -> Vec<usize> {
+    let mut histogram = vec![0; buckets];
+    if buckets > 0 {
+        for profile in profiles {
+            histogram[risk_bucket(profile.risk_tolerance, buckets)] += 1;
+        }
+    }
+    histogram
+}
+
+/// Interval parameters of a profile.
+const fn interval_key(profile: &BehaviorProfile) -> IntervalKey {
+    (
+        profile.action_interval_secs,
+        profile.action_interval_jitter_pct,
+        profile.burst_spacing_secs,
+    )
+}
+
+/// Wallets per set of interval parameters.
+fn interval_groups<'a>(
+    profiles: impl Iterator<Item = &'a BehaviorProfile>,
+) -> HashMap<IntervalKey, usize> {
+    let mut groups = HashMap::new();
+    for profile in profiles {
+        *groups.entry(interval_key(profile)).or_default() += 1;
+    }
+    groups
+}
+
+/// A new action interval for `profile` that `is_free`, a few seconds
+/// from the current one in a seeded direction.
+///
+/// The interval stays above the burst spacing of a bursting profile.
+fn nudge_interval(
+    profile: &BehaviorProfile,
+    rng: &mut StdRng,
+    mut is_free: impl FnMut(IntervalKey) -> bool,
+) -> u64 {
+    let interval = profile.action_interval_secs;
+    let floor = if profile.burst_probability > 0.0 {
+        profile.burst_spacing_secs + 1
+    } else {
+        1
+    };
+    let spread = (interval / 20).max(1);
+    let start = rng.random_range(1..=spread);
+    let down_first = rng.random_bool(0.5);
+
+    (start..)
+        .flat_map(|offset| {
+            let down = interval.checked_sub(offset).filter(|secs| *secs >= floor);
+            let up = interval.checked_add(offset);
+            if down_first { [down, up] } else { [up, down] }
+        })
+        .flatten()
+        .find(|&candidate| {
+            is_free((
+                candidate,
+                profile.action_interval_jitter_pct,
+                profile.burst_spacing_secs,
+            ))
+        })
+        .unwrap_or(interval)
+}
+
+/// Wallets active in each hour of the day.
+fn hourly_coverage<'a>(profiles: impl Iterator<Item = &'a BehaviorProfile>) -> [usize; 24] {
+    let mut coverage = [0; 24];
+    for profile in profiles {
+        for (hour, slot) in (0..24).zip(coverage.iter_mut()) {
+            if profile.is_active_hour(hour) {
+                *slot += 1;
+            }
+        }
+    }
+    coverage
+}
+
+/// Check whether both ends of the active window are valid hours.
+const fn has_valid_hours(profile: &BehaviorProfile) -> bool {
+    profile.active_hours_start <= 23 && profile.active_hours_end <= 23
+}
+
+/// Shifts of the active window that bring `hour` into it, shortest first:
+/// later so it ends at `hour`, or earlier so it starts there.
+fn shifts_to_cover(profile: &BehaviorProfile, hour: u8) -> Vec<i32> {
+    let later = (i32::from(hour) - i32::from(profile.active_hours_end)).rem_euclid(24);
+    let earlier = (i32::from(profile.active_hours_start) - i32::from(hour)).rem_euclid(24);
+    if later <= earlier {
+        vec![later, -earlier]
+    } else {
+        vec![-earlier, later]
+    }
+}
+
+/// `profile` with its active window moved by `shift` hours.
+fn shifted_window(profile: &BehaviorProfile, shift: i32) -> BehaviorProfile {
+    let mut shifted = profile.clone();
+    shifted.active_hours_start = BehaviorProfile::shift_hour(profile.active_hours_start, shift);
+    shifted.active_hours_end = BehaviorProfile::shift_hour(profile.active_hours_end, shift);
+    shifted
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn fleet(count: usize, base: &BehaviorProfile) -> Vec<(String, BehaviorProfile)> {
+        (0..count)
+            .map(|i| (format!("w{i:02}"), base.clone()))
+            .collect()
+    }
+
+    fn active_hours(profile: &BehaviorProfile) -> usize {
+        (0..24).filter(|&hour| profile.is_active_hour(hour)).count()
+    }
+
+    fn mixed_fleet() -> Vec<(String, BehaviorProfile)> {
+        BehaviorProfile::PRESETS
+            .into_iter()
+            .filter_map(BehaviorProfile::preset)
+            .cycle()
+            .take(40)
+            .enumerate()
+            .map(|(i, profile)| {
+                let seed = u64::try_from(i).unwrap_or_default();
+                (
+                    format!("w{i:02}"),
+                    BehaviorProfile::personalize(&profile, seed),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn default_targets_leave_profiles_alone() {
+        let fleet = mixed_fleet();
+        let composition = FleetComposer::new(DiversityTargets::default(), 1).compose(fleet.clone());
+
+        for (id, profile) in &fleet {
+            assert_eq!(composition.get(id), Some(profile));
+        }
+        assert_eq!(composition.report().adjusted_wallets, 0);
+        assert!(composition.report().is_satisfied());
+    }
+
+    #[test]
+    fn hourly_coverage_is_met() {
+        for (base, share) in [
+            (BehaviorProfile::whale(), 0.25),
+            (BehaviorProfile::casual(), 0.3),
+            (BehaviorProfile::grinder(), 0.5),
+        ] {
+            let targets = DiversityTargets {
+                min_hourly_coverage: share,
+                ..DiversityTargets::default()
+            };
+            let composition = FleetComposer::new(targets, 3).compose(fleet(20, &base));
+            let report = composition.report();
+
+            assert!(
+                report.coverage_met(),
+                "{}: {:?}",
+                base.name,
+                report.hourly_coverage
+            );
+            assert!(report.adjusted_wallets > 0);
+            assert!(composition.profiles().all(|(_, p)| p.is_valid()));
+            for (_, profile) in composition.profiles() {
+                // Windows move, they do not grow or shrink
+                assert_eq!(active_hours(profile), active_hours(&base));
+            }
+        }
+    }
+
+    #[test]
+    fn unreachable_coverage_is_reported() {
+        // Two 9-hour windows cannot cover all 24 hours
+        let targets = DiversityTargets {
+            min_hourly_coverage: 0.5,
+            ..DiversityTargets::default()
+        };
+        let composition =
+            FleetComposer::new(targets, 3).compose(fleet(2, &BehaviorProfile::whale()));
+
+        assert!(!composition.report().coverage_met());
+        assert!(!composition.report().is_satisfied());
+    }
+
+    #[test]
+    fn risk_buckets_hold_their_quotas() {
+        for shares in [
+            vec![1.0, 1.0, 1.0, 1.0],
+            vec![0.5, 0.3, 0.2],
+            vec![0.0, 0.0, 1.0],
+        ] {
+            let targets = DiversityTargets {
+                risk_buckets: shares.clone(),
+                ..DiversityTargets::default()
+            };
+            let composition = FleetComposer::new(targets, 5).compose(mixed_fleet());
+            let report = composition.report();
+
+            assert_eq!(report.risk_quotas.iter().sum::<usize>(), 40);
+            assert!(report.risk_met(), "{shares:?}: {:?}", report.risk_histogram);
+            assert!(composition.profiles().all(|(_, p)| p.is_valid()));
+        }
+    }
+
+    #[test]
+    fn risk_ranks_are_kept() {
+        let targets = DiversityTargets {
+            risk_buckets: vec![3.0, 1.0],
+            ..DiversityTargets::default()
+        };
+        let fleet = vec![
+            ("whale".to_string(), BehaviorProfile::whale()),
+            ("casual".to_string(), BehaviorProfile::casual()),
+            ("grinder".to_string(), BehaviorProfile::grinder()),
+            ("sniper".to_string(), BehaviorProfile::sniper()),
+        ];
+        let composition = FleetComposer::new(targets, 5).compose(fleet);
+        let risk = |id: &str| composition.get(id).unwrap().risk_tolerance;
+
+        // Only the sniper keeps the top half; the grinder moves down to fill
+        // the lower one, still above the wallets ranked below it
+        assert!((risk("whale") - 0.2).abs() < f64::EPSILON);
+        assert!((risk("casual") - 0.4).abs() < f64::EPSILON);
+        assert!(risk("grinder") < 0.5);
+        assert!((risk("sniper") - 0.95).abs() < f64::EPSILON);
+        assert_eq!(composition.report().adjusted_wallets, 1);
+    }
+
+    #[test]
+    fn shared_intervals_are_limited() {
+        for (base, share) in [
+            (BehaviorProfile::sniper(), 0.1),
+            (BehaviorProfile::degen(), 0.25),
+            (BehaviorProfile::whale(), 0.0),
+        ] {
+            let targets = DiversityTargets {
+                max_shared_interval_fraction: share,
+                ..DiversityTargets::default()
+            };
+            let composition = FleetComposer::new(targets, 9).compose(fleet(20, &base));
+            let report = composition.report();
+
+            assert!(report.intervals_met(), "{}: {report:?}", base.name);
+            let groups = interval_groups(composition.profiles().map(|(_, p)| p));
+            assert!(
+                groups
+                    .values()
+                    .all(|&wallets| wallets <= report.allowed_shared_intervals)
+            );
+            assert!(composition.profiles().all(|(_, p)| p.is_valid()));
+            for (_, profile) in composition.profiles() {
+                let drift = profile
+                    .action_interval_secs
+                    .abs_diff(base.action_interval_secs);
+                assert!(
+                    drift <= base.action_interval_secs / 10,
+                    "interval drifted by {drift}s"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn targets_combine() {
+        let targets = DiversityTargets {
+            min_hourly_coverage: 0.3,
+            risk_buckets: vec![0.2, 0.5, 0.3],
+            max_shared_interval_fraction: 0.05,
+        };
+        let composition = FleetComposer::new(targets, 11).compose(mixed_fleet());
+
+        assert!(
+            composition.report().is_satisfied(),
+            "{:?}",
+            composition.report()
+        );
+        assert!(composition.profiles().all(|(_, p)| p.is_valid()));
+    }
+
+    #[test]
+    fn composition_is_reproducible() {
+        let targets = DiversityTargets {
+            min_hourly_coverage: 0.4,
+            risk_buckets: vec![1.0, 2.0, 1.0],
+            max_shared_interval_fraction: 0.1,
+        };
+        let first = FleetComposer::new(targets.clone(), 42).compose(mixed_fleet());
+        let again = FleetComposer::new(targets.clone(), 42).compose(mixed_fleet());
+        let reseeded = FleetComposer::new(targets, 43).compose(mixed_fleet());
+
+        assert_eq!(first, again);
+        assert_ne!(first, reseeded);
+    }
+
+    #[test]
+    fn quotas_use_largest_remainders() {
+        assert_eq!(quotas(&[1.0, 1.0, 1.0], 10), [4, 3, 3]);
+        assert_eq!(quotas(&[0.5, 0.3, 0.2], 7), [4, 2, 1]);
+        assert_eq!(quotas(&[0.0, 3.0], 5), [0, 5]);
+        assert!(quotas(&[0.0, 0.0], 5).is_empty());
+        assert!(quotas(&[], 5).is_empty());
+    }
+}
@@ -23,13 +23,43 @@
 //! assert!(profile.risk_tolerance > 0.8);
 //! assert!(profile.activity_level > 10.0);
 //! ```
+//!
+//! # Personalization
+//!
+//! Wallets sharing a profile would otherwise act on the same rhythm.
+//! [`BehaviorProfile::personalize`] gives each wallet its own variation of a
+//! base profile, derived from a seed such as [`personality_seed`] of its
+//! address, so a wallet keeps the same personality across restarts.
+//!
+//! # Catalog
+//!
+//! A [`ProfileCatalog`] holds the profiles a fleet's wallets refer to by
+//! name, resolved once from configuration.
+//!
+//! # Composition
+//!
+//! Personalization varies wallets one at a time. A [`FleetComposer`] then
+//! adjusts the personalized profiles of the whole fleet so that together
+//! they cover the day, spread over risk levels and do not share intervals
+//! (see [`DiversityTargets`]).
+
+mod catalog;
+mod composer;
 
+pub use catalog::ProfileCatalog;
+pub use composer::{Composition, CompositionReport, DiversityTargets, FleetComposer};
+
+use std::collections::HashMap;
 use std::ops::RangeInclusive;
 
+use alloy::primitives::{Address, keccak256};
 use chrono::Duration;
-use rand::Rng;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use serde::{Deserialize, Serialize};
 
+use crate::plugins::ActionId;
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // BEHAVIOR PROFILE
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -47,7 +77,9 @@ use serde::{Deserialize, Serialize};
 /// - **action_interval**: Time between actions with jitter
 /// - **active_hours**: UTC hours when the wallet is most active
 /// - **afk_behavior**: Probability and duration of going AFK
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// - **burst_behavior**: Probability, size and spacing of action bursts
+/// - **action_cooldown_secs**: Per-action overrides of plugin cooldowns
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct BehaviorProfile {
     /// Profile name (e.g., "whale", "degen").
     pub name: String,
@@ -100,6 +132,40 @@ pub struct BehaviorProfile {
 
     /// Maximum AFK duration in hours.
     pub afk_max_hours: u64,
+
+    /// Per-action cooldown overrides in seconds.
+    ///
+    /// Takes precedence over the plugin's
+    /// [`default_cooldown`](crate::plugins::ActionPlugin::default_cooldown).
+    /// A value of 0 disables the cooldown for that action.
+    #[serde(default)]
+    pub action_cooldown_secs: HashMap<ActionId, u64>,
+
+    /// Probability that a scheduled action starts a burst (0.0-1.0).
+    ///
+    /// Real users act in clusters: several actions within minutes, then
+    /// silence. During a burst the next actions are spaced by
+    /// `burst_spacing_secs` instead of `action_interval_secs`.
+    #[serde(default)]
+    pub burst_probability: f64,
+
+    /// Number of short-spaced actions in a burst (inclusive range).
+    #[serde(default = "default_burst_size_range")]
+    pub burst_size_range: RangeInclusive<u32>,
+
+    /// Base spacing between actions in a burst in seconds.
+    ///
+    /// Jittered like the action interval; must be below `action_interval_secs`.
+    #[serde(default = "default_burst_spacing_secs")]
+    pub burst_spacing_secs: u64,
+}
+
+const fn default_burst_size_range() -> RangeInclusive<u32> {
+    1..=1
+}
+
+const fn default_burst_spacing_secs() -> u64 {
+    60
 }
 
 impl BehaviorProfile {
@@ -119,6 +185,10 @@ impl BehaviorProfile {
             afk_probability: 0.1,
             afk_min_hours: 4,
             afk_max_hours: 24,
+            action_cooldown_secs: HashMap::new(),
+            burst_probability: 0.0,
+            burst_size_range: 1..=1,
+            burst_spacing_secs: 60,
         }
     }
 
@@ -139,6 +209,31 @@ impl BehaviorProfile {
         Duration::seconds(interval_secs as i64)
     }
 
+    /// Decide whether the next actions come in a burst.
+    ///
+    /// Returns `Some(size)`, the number of short-spaced actions, if a burst
+    /// starts. Profiles that never burst leave the RNG untouched.
+    #[must_use]
+    pub fn maybe_start_burst(&self, rng: &mut impl Rng) -> Option<u32> {
+        if self.burst_probability <= 0.0 || !rng.random_bool(self.burst_probability) {
+            return None;
+        }
+        Some(rng.random_range(self.burst_size_range.clone()))
+    }
+
+    /// Calculate the spacing between two actions of a burst with jitter.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+    pub fn next_burst_interval(&self, rng: &mut impl Rng) -> Duration {
+        let base = self.burst_spacing_secs as f64;
+        let jitter_range = base * (f64::from(self.action_interval_jitter_pct) / 100.0);
+
+        let jitter = rng.random_range(-jitter_range..=jitter_range);
+        let interval_secs = (base + jitter).max(1.0);
+
+        Duration::seconds(interval_secs as i64)
+    }
+
     /// Check if the current hour is within active hours.
     #[must_use]
     pub const fn is_active_hour(&self, hour: u8) -> bool {
@@ -178,6 +273,20 @@ impl BehaviorProfile {
         }
     }
 
+    /// Resolve the cooldown of an action for this profile.
+    ///
+    /// A profile override beats the plugin default; a zero override (or
+    /// plugin default) means no cooldown.
+    #[must_use]
+    #[allow(clippy::cast_possible_wrap)] // cooldowns will not exceed i64::MAX seconds
+    pub fn cooldown_for(&self, action: &str, plugin_default: Option<Duration>) -> Option<Duration> {
+        self.action_cooldown_secs
+            .get(action)
+            .map(|secs| Duration::seconds(*secs as i64))
+            .or(plugin_default)
+            .filter(|cooldown| *cooldown > Duration::zero())
+    }
+
     /// Get the active hours as a range (for display/serialization).
     #[must_use]
     pub const fn active_hours(&self) -> RangeInclusive<u8> {
@@ -195,6 +304,7 @@ impl BehaviorProfile {
     /// - ~2 actions per hour
     /// - Very patient (0.9)
     /// - Long intervals with high jitter
+    /// - Never acts in bursts
     /// - Sometimes goes AFK for days
     #[must_use]
     pub fn whale() -> Self {
@@ -211,6 +321,10 @@ impl BehaviorProfile {
             afk_probability: 0.1,
             afk_min_hours: 12,
             afk_max_hours: 48,
+            action_cooldown_secs: HashMap::new(),
+            burst_probability: 0.0,
+            burst_size_range: 1..=1,
+            burst_spacing_secs: 60,
         }
     }
 
@@ -237,6 +351,10 @@ impl BehaviorProfile {
             afk_probability: 0.05,
             afk_min_hours: 4,
             afk_max_hours: 12,
+            action_cooldown_secs: HashMap::new(),
+            burst_probability: 0.05,
+            burst_size_range: 2..=3,
+            burst_spacing_secs: 300,
         }
     }
 
@@ -263,6 +381,10 @@ impl BehaviorProfile {
             afk_probability: 0.02,
             afk_min_hours: 1,
             afk_max_hours: 4,
+            action_cooldown_secs: HashMap::new(),
+            burst_probability: 0.15,
+            burst_size_range: 2..=4,
+            burst_spacing_secs: 120,
         }
     }
 
@@ -289,6 +411,10 @@ impl BehaviorProfile {
             afk_probability: 0.2,
             afk_min_hours: 6,
             afk_max_hours: 72,
+            action_cooldown_secs: HashMap::new(),
+            burst_probability: 0.1,
+            burst_size_range: 2..=3,
+            burst_spacing_secs: 600,
         }
     }
 
@@ -299,6 +425,7 @@ impl BehaviorProfile {
     /// - ~12 actions per hour
     /// - Very impatient (0.1)
     /// - 10-minute base interval
+    /// - Often acts in bursts of 3-6 actions 90 seconds apart
     /// - Moderate AFK (recharging between hunts)
     #[must_use]
     pub fn sniper() -> Self {
@@ -315,9 +442,106 @@ impl BehaviorProfile {
             afk_probability: 0.3,  // But takes breaks
             afk_min_hours: 2,
             afk_max_hours: 24,
+            action_cooldown_secs: HashMap::new(),
+            burst_probability: 0.35,
+            burst_size_range: 3..=6,
+            burst_spacing_secs: 90,
+        }
+    }
+
+    /// Names of the preset profiles, see [`preset`](Self::preset).
+    pub const PRESETS: [&'static str; 5] = ["whale", "grinder", "degen", "casual", "sniper"];
+
+    /// Look up a preset profile by name.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fleet_core::profiles::BehaviorProfile;
+    ///
+    /// assert_eq!(BehaviorProfile::preset("whale"), Some(BehaviorProfile::whale()));
+    /// assert_eq!(BehaviorProfile::preset("shark"), None);
+    /// ```
+    #[must_use]
+    pub fn preset(name: &str) -> Option<Self> {
+        match name {
+            "whale" => Some(Self::whale()),
+            "grinder" => Some(Self::grinder()),
+            "degen" => Some(Self::degen()),
+            "casual" => Some(Self::casual()),
+            "sniper" => Some(Self::sniper()),
+            _ => None,
         }
     }
 
+    // ─────────────────────────────────────────────────────────────────────────
+    // PERSONALIZATION
+    // ─────────────────────────────────────────────────────────────────────────
+
+    /// Derive a wallet's own variation of `base` from `seed`.
+    ///
+    /// The same seed always gives the same profile:
+    ///
+    /// - Intervals (action and burst spacing) are scaled by up to ±15%
+    /// - `risk_tolerance` moves by up to ±0.05
+    /// - The active hours window shifts by up to 2 hours either way
+    ///
+    /// Values stay within the bounds of [`validate`](Self::validate), so a
+    /// valid base gives a valid profile.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use alloy::primitives::Address;
+    /// use fleet_core::profiles::{BehaviorProfile, personality_seed};
+    ///
+    /// let base = BehaviorProfile::grinder();
+    /// let seed = personality_seed(Address::repeat_byte(1));
+    /// let profile = BehaviorProfile::personalize(&base, seed);
+    /// assert_eq!(profile, BehaviorProfile::personalize(&base, seed));
+    /// assert!(profile.is_valid());
+    /// ```
+    #[must_use]
+    pub fn personalize(base: &Self, seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let scale = rng.random_range(0.85..=1.15);
+        let risk_shift = rng.random_range(-0.05..=0.05);
+        let hour_shift = rng.random_range(-2_i32..=2);
+
+        let mut profile = base.clone();
+        profile.action_interval_secs = Self::scale_secs(base.action_interval_secs, scale).max(1);
+        profile.burst_spacing_secs = Self::scale_secs(base.burst_spacing_secs, scale);
+        if base.burst_spacing_secs < base.action_interval_secs {
+            // Rounding must not push the spacing up to the interval
+            profile.burst_spacing_secs = profile
+                .burst_spacing_secs
+                .min(profile.action_interval_secs - 1);
+        }
+        if (0.0..=1.0).contains(&base.risk_tolerance) {
+            profile.risk_tolerance = (base.risk_tolerance + risk_shift).clamp(0.0, 1.0);
+        }
+        profile.active_hours_start = Self::shift_hour(base.active_hours_start, hour_shift);
+        profile.active_hours_end = Self::shift_hour(base.active_hours_end, hour_shift);
+        profile
+    }
+
+    /// Scale a number of seconds, rounding to the nearest second.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)] // Positive, scale <= 1.15
+    #[allow(clippy::cast_precision_loss)] // Intervals stay far below 2^52 seconds
+    fn scale_secs(secs: u64, scale: f64) -> u64 {
+        (secs as f64 * scale).round() as u64
+    }
+
+    /// Shift an hour of the day, wrapping around midnight. Invalid hours are
+    /// left alone.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)] // rem_euclid(24) is 0-23
+    fn shift_hour(hour: u8, shift: i32) -> u8 {
+        if hour > 23 {
+            return hour;
+        }
+        (i32::from(hour) + shift).rem_euclid(24) as u8
+    }
+
     // ─────────────────────────────────────────────────────────────────────────
     // VALIDATION
     // ─────────────────────────────────────────────────────────────────────────
@@ -334,10 +558,13 @@ impl BehaviorProfile {
     /// | `patience` | 0.0-1.0 | Probability value |
     /// | `off_hours_factor` | 0.0-1.0 | Probability value |
     /// | `afk_probability` | 0.0-1.0 | Probability value |
+    /// | `burst_probability` | 0.0-1.0 | Probability value |
     /// | `active_hours_*` | 0-23 | Valid UTC hours |
     /// | `activity_level` | > 0 | Must be positive |
     /// | `action_interval_secs` | > 0 | Must be positive |
     /// | `afk_min_hours` | <= afk_max_hours | Logical ordering |
+    /// | `burst_size_range` | >= 1, start <= end | When bursting |
+    /// | `burst_spacing_secs` | < action_interval_secs | When bursting |
     ///
     /// # Example
     ///
@@ -382,6 +609,12 @@ impl BehaviorProfile {
                 value: self.afk_probability,
             });
         }
+        if !(0.0..=1.0).contains(&self.burst_probability) {
+            errors.push(ProfileValidationError::InvalidProbability {
+                field: "burst_probability",
+                value: self.burst_probability,
+            });
+        }
 
         // Hours must be 0-23
         if self.active_hours_start > 23 {
@@ -418,6 +651,38 @@ impl BehaviorProfile {
             });
         }
 
+        if self.burst_probability > 0.0 {
+            errors.extend(self.validate_bursts());
+        }
+
+        errors
+    }
+
+    /// Validate the burst fields of a profile that bursts.
+    fn validate_bursts(&self) -> Vec<ProfileValidationError> {
+        let mut errors = Vec::new();
+        let (min, max) = (*self.burst_size_range.start(), *self.burst_size_range.end());
+
+        if min == 0 {
+            errors.push(ProfileValidationError::NonPositive {
+                field: "burst_size_range",
+            });
+        }
+        if min > max {
+            errors.push(ProfileValidationError::InvalidRange {
+                field: "burst_size_range",
+                min: u64::from(min),
+                max: u64::from(max),
+            });
+        }
+        if self.burst_spacing_secs >= self.action_interval_secs {
+            errors.push(ProfileValidationError::NotBelow {
+                field: "burst_spacing_secs",
+                value: self.burst_spacing_secs,
+                limit: self.action_interval_secs,
+            });
+        }
+
         errors
     }
 
@@ -465,6 +730,29 @@ pub enum ProfileValidationError {
         /// Maximum value.
         max: u64,
     },
+    /// A field must lie below a limit set by another field.
+    NotBelow {
+        /// Field name.
+        field: &'static str,
+        /// Invalid value.
+        value: u64,
+        /// Exclusive upper limit.
+        limit: u64,
+    },
+}
+
+impl ProfileValidationError {
+    /// Name of the invalid field.
+    #[must_use]
+    pub const fn field(&self) -> &'static str {
+        match self {
+            Self::InvalidProbability { field, .. }
+            | Self::InvalidHour { field, .. }
+            | Self::NonPositive { field }
+            | Self::InvalidRange { field, .. }
+            | Self::NotBelow { field, .. } => field,
+        }
+    }
 }
 
 impl std::fmt::Display for ProfileValidationError {
@@ -482,6 +770,9 @@ impl std::fmt::Display for ProfileValidationError {
             Self::InvalidRange { field, min, max } => {
                 write!(f, "{field} range is invalid: min ({min}) > max ({max})")
             }
+            Self::NotBelow { field, value, limit } => {
+                write!(f, "{field} must be below {limit}, got {value}")
+            }
         }
     }
 }
@@ -494,6 +785,18 @@ impl Default for BehaviorProfile {
     }
 }
 
+/// Personalization seed of a wallet, see [`BehaviorProfile::personalize`].
+///
+/// Taken from the keccak256 hash of the address, so it is the same on every
+/// run and machine.
+#[must_use]
+pub fn personality_seed(address: Address) -> u64 {
+    let hash = keccak256(address);
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&hash[..8]);
+    u64::from_be_bytes(bytes)
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // TESTS
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -517,6 +820,14 @@ mod tests {
         assert_eq!(BehaviorProfile::sniper().name, "sniper");
     }
 
+    #[test]
+    fn presets_by_name() {
+        for name in BehaviorProfile::PRESETS {
+            assert_eq!(BehaviorProfile::preset(name).map(|p| p.name), Some(name.to_string()));
+        }
+        assert!(BehaviorProfile::preset("Whale").is_none());
+    }
+
     #[test]
     fn risk_tolerance_ordering() {
         // Whale < Casual < Grinder < Degen < Sniper
@@ -666,6 +977,40 @@ mod tests {
         )));
     }
 
+    #[test]
+    fn validation_catches_invalid_bursts() {
+        let mut profile = BehaviorProfile::sniper();
+        profile.burst_size_range = 0..=3;
+        profile.burst_spacing_secs = profile.action_interval_secs;
+
+        let errors = profile.validate();
+        assert!(errors.contains(&ProfileValidationError::NonPositive {
+            field: "burst_size_range"
+        }));
+        assert!(errors.iter().any(|e| matches!(e,
+            ProfileValidationError::NotBelow { field: "burst_spacing_secs", value: 600, limit: 600 }
+        )));
+
+        // Burst fields only matter for profiles that burst
+        profile.burst_probability = 0.0;
+        assert!(profile.is_valid());
+    }
+
+    #[test]
+    fn burst_presets() {
+        let mut rng = test_rng();
+        let whale = BehaviorProfile::whale();
+        assert!((0..1000).all(|_| whale.maybe_start_burst(&mut rng).is_none()));
+
+        let sniper = BehaviorProfile::sniper();
+        let sizes: Vec<_> = (0..1000).filter_map(|_| sniper.maybe_start_burst(&mut rng)).collect();
+        assert!(sizes.len() > 200, "sniper should burst often, got {}", sizes.len());
+        assert!(sizes.iter().all(|size| sniper.burst_size_range.contains(size)));
+
+        let spacing = sniper.next_burst_interval(&mut rng);
+        assert!(spacing < sniper.next_interval(&mut rng));
+    }
+
     #[test]
     fn validation_error_display() {
         let err = ProfileValidationError::InvalidProbability {
@@ -674,4 +1019,83 @@ mod tests {
         };
         assert_eq!(err.to_string(), "risk_tolerance must be 0.0-1.0, got 1.5");
     }
+
+    #[test]
+    fn profile_cooldown_overrides_plugin_default() {
+        let mut profile = BehaviorProfile::new("test");
+        profile.action_cooldown_secs.insert("p.bet".into(), 60);
+        profile.action_cooldown_secs.insert("p.claim".into(), 0);
+        let plugin_default = Some(Duration::minutes(10));
+
+        // Override beats the plugin default
+        assert_eq!(profile.cooldown_for("p.bet", plugin_default), Some(Duration::seconds(60)));
+        // No override: plugin default applies
+        assert_eq!(profile.cooldown_for("p.stake", plugin_default), plugin_default);
+        assert_eq!(profile.cooldown_for("p.stake", None), None);
+        // Zero override disables the cooldown
+        assert_eq!(profile.cooldown_for("p.claim", plugin_default), None);
+    }
+
+    #[test]
+    fn personalization_is_stable_per_address() {
+        let base = BehaviorProfile::sniper();
+        let address = Address::repeat_byte(0xAB);
+        let first = BehaviorProfile::personalize(&base, personality_seed(address));
+        let again = BehaviorProfile::personalize(&base, personality_seed(address));
+        assert_eq!(first, again);
+        assert_eq!(first.name, base.name);
+        assert_ne!(personality_seed(address), personality_seed(Address::ZERO));
+    }
+
+    #[test]
+    #[allow(clippy::cast_precision_loss)]
+    fn personalization_varies_within_bounds() {
+        for base in [
+            BehaviorProfile::whale(),
+            BehaviorProfile::degen(),
+            BehaviorProfile::sniper(),
+        ] {
+            let profiles: Vec<_> = (0..=u8::MAX)
+                .map(|byte| {
+                    let seed = personality_seed(Address::repeat_byte(byte));
+                    BehaviorProfile::personalize(&base, seed)
+                })
+                .collect();
+
+            for profile in &profiles {
+                assert!(profile.is_valid(), "{:?}", profile.validate());
+                let ratio = profile.action_interval_secs as f64 / base.action_interval_secs as f64;
+                assert!((0.85..=1.15).contains(&ratio), "interval ratio {ratio}");
+                assert!((profile.risk_tolerance - base.risk_tolerance).abs() <= 0.05 + 1e-9);
+            }
+
+            // Spread over the whole range rather than collapsed onto the base
+            let intervals: std::collections::HashSet<_> =
+                profiles.iter().map(|p| p.action_interval_secs).collect();
+            assert!(intervals.len() > 20, "{} distinct intervals", intervals.len());
+            let starts: std::collections::HashSet<_> =
+                profiles.iter().map(|p| p.active_hours_start).collect();
+            assert_eq!(starts.len(), 5, "shifts of -2..=2 hours");
+            let (low, high) = profiles
+                .iter()
+                .map(|p| p.risk_tolerance)
+                .fold((f64::MAX, f64::MIN), |(lo, hi), r| (lo.min(r), hi.max(r)));
+            assert!(high - low > 0.05, "risk spread {low}-{high}");
+        }
+    }
+
+    #[test]
+    fn personalized_hours_wrap_around_midnight() {
+        let mut base = BehaviorProfile::new("night");
+        base.active_hours_start = 23;
+        base.active_hours_end = 1;
+        for byte in 0..=u8::MAX {
+            let seed = personality_seed(Address::repeat_byte(byte));
+            let profile = BehaviorProfile::personalize(&base, seed);
+            assert!(profile.is_valid());
+            // The window keeps its length
+            let length = (24 + profile.active_hours_end - profile.active_hours_start) % 24;
+            assert_eq!(length, 2);
+        }
+    }
 }
@@ -0,0 +1,605 @@
+//! Spend budgets of wallets and of the whole fleet.
+//!
+//! See [`BudgetManager`].
+
+use std::collections::HashMap;
+
+use alloy::primitives::U256;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::clock::{SharedClock, system_clock};
+use crate::plugins::{ActionResult, ActionStatus};
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// SPEND
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Resources used by actions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Spend {
+    /// Native gas in wei.
+    pub gas_wei: U256,
+
+    /// DATA staked or bet, in wei.
+    pub data: U256,
+
+    /// Actions sent to the chain.
+    pub actions: u64,
+}
+
+impl Spend {
+    /// Nothing spent.
+    pub const ZERO: Self = Self {
+        gas_wei: U256::ZERO,
+        data: U256::ZERO,
+        actions: 0,
+    };
+
+    /// A single action that uses no gas or DATA.
+    #[must_use]
+    pub const fn action() -> Self {
+        Self {
+            gas_wei: U256::ZERO,
+            data: U256::ZERO,
+            actions: 1,
+        }
+    }
+
+    /// Set the native gas spent.
+    #[must_use]
+    pub const fn with_gas_wei(mut self, gas_wei: U256) -> Self {
+        self.gas_wei = gas_wei;
+        self
+    }
+
+    /// Set the DATA spent.
+    #[must_use]
+    pub const fn with_data(mut self, data: U256) -> Self {
+        self.data = data;
+        self
+    }
+
+    /// What an executed action actually spent, given what it was estimated to.
+    ///
+    /// Actions that never reached the chain spend nothing. Gas is taken from
+    /// the result whatever the estimate said; DATA is the estimated amount,
+    /// and only leaves the wallet if the action succeeded.
+    #[must_use]
+    pub fn actual(result: &ActionResult, estimate: &Self) -> Self {
+        let succeeded = result.status == ActionStatus::Succeeded;
+        if !succeeded && result.tx_hash.is_none() {
+            return Self::ZERO;
+        }
+        Self {
+            gas_wei: result.gas_cost_wei().map_or(U256::ZERO, U256::from),
+            data: if succeeded { estimate.data } else { U256::ZERO },
+            actions: 1,
+        }
+    }
+
+    /// Sum of two spends, saturating at the numeric bounds.
+    #[must_use]
+    pub const fn saturating_add(&self, other: &Self) -> Self {
+        Self {
+            gas_wei: self.gas_wei.saturating_add(other.gas_wei),
+            data: self.data.saturating_add(other.data),
+            actions: self.actions.saturating_add(other.actions),
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// CAPS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Caps on what may be spent over some period. Unset caps are unlimited.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SpendLimit {
+    /// Maximum native gas in wei.
+    pub gas_wei: Option<U256>,
+
+    /// Maximum DATA staked or bet, in wei.
+    pub data: Option<U256>,
+
+    /// Maximum actions.
+    pub actions: Option<u64>,
+}
+
+impl SpendLimit {
+    /// No caps.
+    pub const UNLIMITED: Self = Self {
+        gas_wei: None,
+        data: None,
+        actions: None,
+    };
+
+    /// Check whether `spent` has reached any cap.
+    #[must_use]
+    pub fn is_reached_by(&self, spent: &Spend) -> bool {
+        self.gas_wei.is_some_and(|cap| spent.gas_wei >= cap)
+            || self.data.is_some_and(|cap| spent.data >= cap)
+            || self.actions.is_some_and(|cap| spent.actions >= cap)
+    }
+
+    /// Check whether `extra` can be spent on top of `spent` without going
+    /// over a cap.
+    #[must_use]
+    pub fn allows(&self, spent: &Spend, extra: &Spend) -> bool {
+        let total = spent.saturating_add(extra);
+        !self.is_reached_by(spent)
+            && self.gas_wei.is_none_or(|cap| total.gas_wei <= cap)
+            && self.data.is_none_or(|cap| total.data <= cap)
+            && self.actions.is_none_or(|cap| total.actions <= cap)
+    }
+}
+
+/// Daily and lifetime caps of a wallet or the fleet.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BudgetCaps {
+    /// Caps per rolling daily window.
+    pub daily: SpendLimit,
+
+    /// Caps over all time.
+    pub lifetime: SpendLimit,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// LEDGER
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// What a wallet or the fleet spent in its current daily window and overall.
+///
+/// A daily window opens with the first spend after the previous one closed
+/// and lasts [`WINDOW`](Self::WINDOW). Wallet ledgers are persisted with the
+/// [`WalletState`](crate::wallet::WalletState) so that windows survive
+/// restarts.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpendLedger {
+    /// When the current daily window opened, if one was ever opened.
+    #[serde(default)]
+    pub window_start: Option<DateTime<Utc>>,
+
+    /// Spent in the current daily window.
+    #[serde(default)]
+    pub daily: Spend,
+
+    /// Spent overall.
+    #[serde(default)]
+    pub lifetime: Spend,
+}
+
+impl SpendLedger {
+    /// Length of a daily window.
+    pub const WINDOW: Duration = Duration::days(1);
+
+    /// When the daily window open at `now` closes, if one is open.
+    ///
+    /// A window is open until a full [`WINDOW`](Self::WINDOW) after it
+    /// started, even if `now` lies before its start because the clock went
+    /// back across a restart. Windows therefore never roll over early.
+    #[must_use]
+    pub fn window_end_at(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        self.window_start
+            .map(|start| start + Self::WINDOW)
+            .filter(|end| now < *end)
+    }
+
+    /// Spent in the daily window open at `now`.
+    #[must_use]
+    pub fn daily_at(&self, now: DateTime<Utc>) -> Spend {
+        if self.window_end_at(now).is_some() {
+            self.daily
+        } else {
+            Spend::ZERO
+        }
+    }
+
+    /// Record `spend` at `now`, opening a new daily window if none is open.
+    pub fn record_at(&mut self, spend: &Spend, now: DateTime<Utc>) {
+        if self.window_end_at(now).is_none() {
+            self.window_start = Some(now);
+            self.daily = Spend::ZERO;
+        }
+        self.daily = self.daily.saturating_add(spend);
+        self.lifetime = self.lifetime.saturating_add(spend);
+    }
+
+    /// Where the ledger stands against `caps` at `now`.
+    #[must_use]
+    pub fn status_at(&self, caps: &BudgetCaps, now: DateTime<Utc>) -> BudgetStatus {
+        if caps.lifetime.is_reached_by(&self.lifetime) {
+            return BudgetStatus::LifetimeExhausted;
+        }
+        match self.window_end_at(now) {
+            Some(until) if caps.daily.is_reached_by(&self.daily) => {
+                BudgetStatus::DailyExhausted { until }
+            }
+            _ => BudgetStatus::Available,
+        }
+    }
+
+    /// Check whether `extra` can be spent at `now` without going over `caps`.
+    #[must_use]
+    pub fn allows_at(&self, caps: &BudgetCaps, extra: &Spend, now: DateTime<Utc>) -> bool {
+        caps.daily.allows(&self.daily_at(now), extra) && caps.lifetime.allows(&self.lifetime, extra)
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// STATUS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Where a wallet or the fleet stands against its budget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BudgetStatus {
+    /// No cap is reached.
+    Available,
+
+    /// A daily cap is reached; spending resumes when the window rolls over.
+    DailyExhausted {
+        /// When the daily window rolls over.
+        until: DateTime<Utc>,
+    },
+
+    /// A lifetime cap is reached; spending does not resume on its own.
+    LifetimeExhausted,
+}
+
+impl BudgetStatus {
+    /// Check whether a cap is reached.
+    #[must_use]
+    pub const fn is_exhausted(&self) -> bool {
+        !matches!(self, Self::Available)
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// BUDGET MANAGER
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Daily and lifetime spend caps per wallet and for the fleet as a whole.
+///
+/// Tracks native gas, DATA and action counts in a [`SpendLedger`] per wallet
+/// plus one for the fleet. An action may only run if its estimated spend fits
+/// under both the wallet's and the fleet's caps; afterwards its actual spend
+/// is recorded in full, even where it exceeds the estimate. A wallet whose
+/// cap is reached is budget-exhausted until its daily window rolls over, or
+/// for good if a lifetime cap is reached.
+///
+/// Unlike a tripped [`CircuitBreaker`](super::CircuitBreaker), exhaustion
+/// says nothing about the wallet's health. It is not reset by successes and
+/// ends only with the window.
+///
+/// # Restarts
+///
+/// Wallet ledgers are restored from the persisted wallet state with
+/// [`restore`](Self::restore). The fleet ledger is rebuilt from them: it
+/// counts every wallet's open daily spend and keeps the latest of their
+/// windows, so a restart never hands the fleet a fresh day early.
+#[derive(Debug)]
+pub struct BudgetManager {
+    /// Caps of the fleet as a whole.
+    fleet_caps: BudgetCaps,
+
+    /// Caps by wallet ID. Wallets without caps are only held to the fleet's.
+    wallet_caps: HashMap<String, BudgetCaps>,
+
+    /// Ledger of the fleet as a whole.
+    fleet: SpendLedger,
+
+    /// Ledgers by wallet ID.
+    wallets: HashMap<String, SpendLedger>,
+
+    /// Source of the current time.
+    clock: SharedClock,
+}
+
+impl BudgetManager {
+    /// Create a budget manager with the given fleet-wide caps.
+    #[must_use]
+    pub fn new(fleet_caps: BudgetCaps) -> Self {
+        Self {
+            fleet_caps,
+            wallet_caps: HashMap::new(),
+            fleet: SpendLedger::default(),
+            wallets: HashMap::new(),
+            clock: system_clock(),
+        }
+    }
+
+    /// Use `clock` for daily windows instead of the system clock.
+    #[must_use]
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Set the caps of a wallet.
+    pub fn set_caps(&mut self, wallet_id: impl Into<String>, caps: BudgetCaps) {
+        self.wallet_caps.insert(wallet_id.into(), caps);
+    }
+
+    /// Restore a wallet's ledger, e.g. from persisted wallet state, and add
+    /// its spend to the fleet ledger.
+    pub fn restore(&mut self, wallet_id: impl Into<String>, ledger: SpendLedger) {
+        let now = self.clock.now();
+        if ledger.window_end_at(now).is_some() {
+            self.fleet.daily = self.fleet.daily.saturating_add(&ledger.daily);
+            self.fleet.window_start = self.fleet.window_start.max(ledger.window_start);
+        }
+        self.fleet.lifetime = self.fleet.lifetime.saturating_add(&ledger.lifetime);
+        self.wallets.insert(wallet_id.into(), ledger);
+    }
+
+    /// Check whether a wallet can spend `estimate` without going over its own
+    /// or the fleet's caps.
+    #[must_use]
+    pub fn can_spend(&self, wallet_id: &str, estimate: &Spend) -> bool {
+        let now = self.clock.now();
+        let ledger = self.wallets.get(wallet_id).cloned().unwrap_or_default();
+        ledger.allows_at(&self.caps(wallet_id), estimate, now)
+            && self.fleet.allows_at(&self.fleet_caps, estimate, now)
+    }
+
+    /// Record what a wallet actually spent, in full.
+    ///
+    /// # Returns
+    ///
+    /// The wallet's status afterwards.
+    pub fn record_spend(&mut self, wallet_id: &str, spend: &Spend) -> BudgetStatus {
+        let now = self.clock.now();
+        let caps = self.caps(wallet_id);
+
+        let fleet_was_exhausted = self.fleet.status_at(&self.fleet_caps, now).is_exhausted();
+        self.fleet.record_at(spend, now);
+        let fleet_status = self.fleet.status_at(&self.fleet_caps, now);
+        if fleet_status.is_exhausted() && !fleet_was_exhausted {
+            warn!(status = ?fleet_status, "Fleet budget exhausted");
+        }
+
+        let ledger = self.wallets.entry(wallet_id.to_string()).or_default();
+        let was_exhausted = ledger.status_at(&caps, now).is_exhausted();
+        ledger.record_at(spend, now);
+        let status = ledger.status_at(&caps, now);
+        if status.is_exhausted() && !was_exhausted {
+            warn!(wallet = %wallet_id, status = ?status, "Wallet budget exhausted");
+        }
+        status
+    }
+
+    /// Where a wallet stands against its own caps.
+    #[must_use]
+    pub fn status(&self, wallet_id: &str) -> BudgetStatus {
+        let caps = self.caps(wallet_id);
+        self.wallets
+            .get(wallet_id)
+            .map_or(BudgetStatus::Available, |ledger| {
+                ledger.status_at(&caps, self.clock.now())
+            })
+    }
+
+    /// Where the fleet stands against its caps.
+    #[must_use]
+    pub fn fleet_status(&self) -> BudgetStatus {
+        self.fleet.status_at(&self.fleet_caps, self.clock.now())
+    }
+
+    /// Get the number of wallets whose own budget is exhausted.
+    #[must_use]
+    pub fn exhausted_count(&self) -> usize {
+        self.wallets
+            .keys()
+            .filter(|id| self.status(id).is_exhausted())
+            .count()
+    }
+
+    /// When a wallet held back by a daily cap may spend again.
+    ///
+    /// The later close of the wallet's and the fleet's open daily windows,
+    /// `None` if neither has one open.
+    #[must_use]
+    pub fn resumes_at(&self, wallet_id: &str) -> Option<DateTime<Utc>> {
+        let now = self.clock.now();
+        let wallet = self
+            .wallets
+            .get(wallet_id)
+            .and_then(|l| l.window_end_at(now));
+        wallet.max(self.fleet.window_end_at(now))
+    }
+
+    /// Get a wallet's ledger, if it ever spent anything.
+    #[must_use]
+    pub fn ledger(&self, wallet_id: &str) -> Option<&SpendLedger> {
+        self.wallets.get(wallet_id)
+    }
+
+    /// Get the fleet's ledger.
+    #[must_use]
+    pub const fn fleet_ledger(&self) -> &SpendLedger {
+        &self.fleet
+    }
+
+    /// Caps of a wallet, unlimited if none are set.
+    fn caps(&self, wallet_id: &str) -> BudgetCaps {
+        self.wallet_caps.get(wallet_id).copied().unwrap_or_default()
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::sync::Arc;
+
+    use alloy::primitives::{Address, TxHash};
+
+    use super::*;
+    use crate::clock::{Clock, VirtualClock};
+    use crate::wallet::WalletState;
+
+    fn daily_actions(max: u64) -> BudgetCaps {
+        BudgetCaps {
+            daily: SpendLimit {
+                actions: Some(max),
+                ..SpendLimit::UNLIMITED
+            },
+            lifetime: SpendLimit::UNLIMITED,
+        }
+    }
+
+    fn manager(fleet_caps: BudgetCaps) -> (Arc<VirtualClock>, BudgetManager) {
+        let clock = Arc::new(VirtualClock::new(Utc::now()));
+        let budget = BudgetManager::new(fleet_caps).with_clock(Arc::clone(&clock) as _);
+        (clock, budget)
+    }
+
+    #[test]
+    fn daily_window_rolls_over() {
+        let (clock, mut budget) = manager(BudgetCaps::default());
+        budget.set_caps("w", daily_actions(2));
+
+        let start = clock.now();
+        budget.record_spend("w", &Spend::action());
+        clock.advance(Duration::hours(12));
+        assert!(budget.can_spend("w", &Spend::action()));
+        let status = budget.record_spend("w", &Spend::action());
+        assert_eq!(
+            status,
+            BudgetStatus::DailyExhausted {
+                until: start + Duration::days(1)
+            }
+        );
+        assert!(!budget.can_spend("w", &Spend::action()));
+        assert_eq!(budget.exhausted_count(), 1);
+        assert_eq!(budget.resumes_at("w"), Some(start + Duration::days(1)));
+
+        // The window is a full day from the first spend, not from the last
+        clock.advance(Duration::hours(12));
+        assert_eq!(budget.status("w"), BudgetStatus::Available);
+        assert!(budget.can_spend("w", &Spend::action()));
+        assert_eq!(budget.ledger("w").map(|l| l.lifetime.actions), Some(2));
+    }
+
+    #[test]
+    fn lifetime_cap_does_not_roll_over() {
+        let (clock, mut budget) = manager(BudgetCaps::default());
+        let data = U256::from(1_000);
+        budget.set_caps(
+            "w",
+            BudgetCaps {
+                daily: SpendLimit::UNLIMITED,
+                lifetime: SpendLimit {
+                    data: Some(data),
+                    ..SpendLimit::UNLIMITED
+                },
+            },
+        );
+
+        // An estimate that would go over the cap is refused outright
+        assert!(!budget.can_spend("w", &Spend::action().with_data(data + U256::from(1))));
+        assert!(budget.can_spend("w", &Spend::action().with_data(data)));
+
+        budget.record_spend("w", &Spend::action().with_data(data));
+        clock.advance(Duration::days(30));
+        assert_eq!(budget.status("w"), BudgetStatus::LifetimeExhausted);
+        assert!(!budget.can_spend("w", &Spend::action()));
+    }
+
+    #[test]
+    fn actual_spend_is_recorded_in_full() {
+        let (_, mut budget) = manager(BudgetCaps::default());
+        let gas_cap = U256::from(150_000);
+        budget.set_caps(
+            "w",
+            BudgetCaps {
+                daily: SpendLimit {
+                    gas_wei: Some(gas_cap),
+                    ..SpendLimit::UNLIMITED
+                },
+                lifetime: SpendLimit::UNLIMITED,
+            },
+        );
+
+        // The estimate knows nothing about gas; the result does
+        let estimate = Spend::action().with_data(U256::from(50));
+        assert!(budget.can_spend("w", &estimate));
+        let result =
+            ActionResult::success_with_gas(TxHash::ZERO, 100_000).with_effective_gas_price(2);
+        let actual = Spend::actual(&result, &estimate);
+        assert_eq!(actual.gas_wei, U256::from(200_000));
+        assert_eq!(actual.data, U256::from(50));
+
+        assert!(budget.record_spend("w", &actual).is_exhausted());
+        let ledger = budget.ledger("w").unwrap();
+        assert_eq!(ledger.daily.gas_wei, U256::from(200_000));
+
+        // Reverted actions burn gas but no DATA; unsent ones spend nothing
+        let reverted = ActionResult::reverted(TxHash::ZERO, "out of gas").with_gas_used(10);
+        assert_eq!(Spend::actual(&reverted, &estimate).data, U256::ZERO);
+        assert_eq!(Spend::actual(&reverted, &estimate).actions, 1);
+        assert_eq!(
+            Spend::actual(&ActionResult::failure("no signer"), &estimate),
+            Spend::ZERO
+        );
+    }
+
+    #[test]
+    fn fleet_cap_trips_before_wallet_caps() {
+        let (_, mut budget) = manager(daily_actions(3));
+        for id in ["a", "b"] {
+            budget.set_caps(id, daily_actions(2));
+        }
+
+        budget.record_spend("a", &Spend::action());
+        budget.record_spend("b", &Spend::action());
+        budget.record_spend("a", &Spend::action());
+
+        // Neither wallet has reached its own cap, but the fleet has
+        assert_eq!(budget.status("b"), BudgetStatus::Available);
+        assert_eq!(budget.exhausted_count(), 1);
+        assert!(budget.fleet_status().is_exhausted());
+        assert!(!budget.can_spend("b", &Spend::action()));
+        assert!(!budget.can_spend("c", &Spend::action()));
+    }
+
+    #[test]
+    fn ledgers_survive_restart() {
+        let (clock, mut budget) = manager(daily_actions(10));
+        budget.set_caps("w", daily_actions(2));
+        budget.record_spend("w", &Spend::action());
+        budget.record_spend("w", &Spend::action());
+
+        let mut wallet = WalletState::new("w".into(), Address::ZERO);
+        wallet.budget = budget.ledger("w").cloned().unwrap_or_default();
+        let json = serde_json::to_value(&wallet).unwrap();
+        let restored: WalletState = serde_json::from_value(json).unwrap();
+        assert_eq!(restored.budget, wallet.budget);
+
+        // The clock went back an hour across the restart: the window must
+        // not close any earlier than it would have
+        let window_end = wallet
+            .budget
+            .window_start
+            .map(|start| start + Duration::days(1));
+        clock.set(clock.now() - Duration::hours(1));
+        let mut restarted =
+            BudgetManager::new(daily_actions(10)).with_clock(Arc::clone(&clock) as _);
+        restarted.set_caps("w", daily_actions(2));
+        restarted.restore("w", restored.budget);
+
+        assert!(!restarted.can_spend("w", &Spend::action()));
+        assert_eq!(restarted.fleet_ledger().daily_at(clock.now()).actions, 2);
+        assert_eq!(restarted.resumes_at("w"), window_end);
+
+        // Snapshots from before budgets load with nothing spent
+        let mut json = serde_json::to_value(&wallet).unwrap();
+        json.as_object_mut().unwrap().remove("budget");
+        let legacy: WalletState = serde_json::from_value(json).unwrap();
+        assert_eq!(legacy.budget, SpendLedger::default());
+    }
+}
@@ -0,0 +1,399 @@
+//! Degradation tiers of a fleet whose RPC endpoints are partly available.
+//!
+//! The service probes its endpoints now and then for what they can still
+//! do, and records each probe's [`Capabilities`] in a [`Degradation`], which
+//! moves the fleet between tiers:
+//!
+//! | Tier | Probes find | The fleet |
+//! |------|-------------|-----------|
+//! | 0, [`Normal`](DegradationTier::Normal) | reads and sends work | acts as usual |
+//! | 1, [`ReadOnly`](DegradationTier::ReadOnly) | sends fail | refreshes and decides, but sends nothing |
+//! | 2, [`Paused`](DegradationTier::Paused) | reads fail too | does nothing but probe |
+//!
+//! Tiers change with hysteresis, so a flapping endpoint does not flap the
+//! fleet: only once `degrade_after` probes in a row found the fleet worse
+//! off than its tier, or `recover_after` probes in a row found it better
+//! off. It then moves only as far as all of those probes agree: a run that
+//! mixes failing sends with failing reads makes it read-only, not paused,
+//! and so does a paused fleet's recovery with a failing send among it.
+//!
+//! ```
+//! use chrono::Utc;
+//! use fleet_core::safety::{Capabilities, Degradation, DegradationSettings, DegradationTier};
+//!
+//! let settings = DegradationSettings { degrade_after: 2, recover_after: 2 };
+//! let mut degradation = Degradation::new(settings);
+//! let sends_failing = Capabilities { reads: true, sends: false, realtime: None };
+//!
+//! assert!(degradation.record(sends_failing, Utc::now()).is_none());
+//! let change = degradation.record(sends_failing, Utc::now()).unwrap();
+//! assert_eq!(change.to, DegradationTier::ReadOnly);
+//! assert_eq!(degradation.tier(), DegradationTier::ReadOnly);
+//! ```
+
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TIERS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// How much of its work a fleet can do, mildest first.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum DegradationTier {
+    /// Tier 0: everything works.
+    #[default]
+    Normal,
+
+    /// Tier 1: sends fail, so wallets only read and decide.
+    ReadOnly,
+
+    /// Tier 2: reads fail too, so the fleet only probes.
+    Paused,
+}
+
+impl DegradationTier {
+    /// Number of the tier, 0 for [`Normal`](Self::Normal).
+    #[must_use]
+    pub const fn level(self) -> u8 {
+        self as u8
+    }
+
+    /// Name of the tier as serialized.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Normal => "normal",
+            Self::ReadOnly => "read_only",
+            Self::Paused => "paused",
+        }
+    }
+}
+
+impl fmt::Display for DegradationTier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "tier {} ({})", self.level(), self.as_str())
+    }
+}
+
+/// What a probe of the fleet's endpoints found working.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Capabilities {
+    /// Reads were answered.
+    pub reads: bool,
+
+    /// Sent transactions reached the node, one way or the other.
+    pub sends: bool,
+
+    /// Transactions sent through the realtime API reached the node, `None`
+    /// where no endpoint offers it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub realtime: Option<bool>,
+}
+
+impl Capabilities {
+    /// The tier a fleet with these capabilities belongs in.
+    #[must_use]
+    pub const fn tier(&self) -> DegradationTier {
+        match (self.reads, self.sends) {
+            (false, _) => DegradationTier::Paused,
+            (true, false) => DegradationTier::ReadOnly,
+            (true, true) => DegradationTier::Normal,
+        }
+    }
+
+    /// What either of two endpoints can do, as wallets fail over between
+    /// them.
+    #[must_use]
+    pub const fn or(self, other: Self) -> Self {
+        Self {
+            reads: self.reads || other.reads,
+            sends: self.sends || other.sends,
+            realtime: match (self.realtime, other.realtime) {
+                (Some(one), Some(other)) => Some(one || other),
+                (one, None) => one,
+                (None, other) => other,
+            },
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// DEGRADATION
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Probes in a row a [`Degradation`] waits for before it changes tiers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DegradationSettings {
+    /// Probes finding the fleet worse off before it degrades.
+    pub degrade_after: u32,
+
+    /// Probes finding the fleet better off before it recovers.
+    pub recover_after: u32,
+}
+
+impl Default for DegradationSettings {
+    fn default() -> Self {
+        Self {
+            degrade_after: 3,
+            recover_after: 5,
+        }
+    }
+}
+
+/// A move of the fleet from one tier to another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TierChange {
+    /// Tier the fleet was in.
+    pub from: DegradationTier,
+
+    /// Tier the fleet is in now.
+    pub to: DegradationTier,
+
+    /// When the probe that moved it ran.
+    pub at: DateTime<Utc>,
+
+    /// What that probe found.
+    pub capabilities: Capabilities,
+}
+
+impl TierChange {
+    /// Whether the fleet moved to a milder tier.
+    #[must_use]
+    pub fn is_recovery(&self) -> bool {
+        self.to < self.from
+    }
+}
+
+/// Degradation of a fleet, as reported in its
+/// [`FleetSnapshot`](crate::metrics::FleetSnapshot).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DegradationStatus {
+    /// Tier the fleet is in.
+    #[serde(default)]
+    pub tier: DegradationTier,
+
+    /// When the fleet moved to its tier, `None` if it never moved.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub since: Option<DateTime<Utc>>,
+
+    /// When the last probe ran, if any did.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_probe: Option<DateTime<Utc>>,
+
+    /// What the last probe found.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub capabilities: Option<Capabilities>,
+
+    /// Probes run.
+    #[serde(default)]
+    pub probes: u64,
+
+    /// Tier changes.
+    #[serde(default)]
+    pub transitions: u64,
+}
+
+/// Tier of a fleet, moved by the probes of its endpoints; see the
+/// [module docs](self).
+#[derive(Debug)]
+pub struct Degradation {
+    settings: DegradationSettings,
+
+    /// Tier the fleet is in.
+    tier: DegradationTier,
+
+    /// Tier the probes in a row so far would move the fleet to, and how many
+    /// they are.
+    pending: Option<(DegradationTier, u32)>,
+
+    /// What is reported, apart from the tier.
+    status: DegradationStatus,
+}
+
+impl Degradation {
+    /// A fleet in tier 0 that changes tiers after the probes `settings`
+    /// asks for.
+    #[must_use]
+    pub fn new(settings: DegradationSettings) -> Self {
+        Self {
+            settings,
+            tier: DegradationTier::Normal,
+            pending: None,
+            status: DegradationStatus::default(),
+        }
+    }
+
+    /// Tier the fleet is in.
+    #[must_use]
+    pub const fn tier(&self) -> DegradationTier {
+        self.tier
+    }
+
+    /// Record what a probe at `at` found, returning the tier change it
+    /// completed, if any.
+    pub fn record(&mut self, capabilities: Capabilities, at: DateTime<Utc>) -> Option<TierChange> {
+        self.status.probes += 1;
+        self.status.last_probe = Some(at);
+        self.status.capabilities = Some(capabilities);
+
+        let found = capabilities.tier();
+        if found == self.tier {
+            self.pending = None;
+            return None;
+        }
+        let worse = found > self.tier;
+        let (target, count) = match self.pending {
+            // Same direction as the run so far: as far as all of it agrees
+            Some((target, count)) if (target > self.tier) == worse => {
+                let target = if worse {
+                    target.min(found)
+                } else {
+                    target.max(found)
+                };
+                (target, count + 1)
+            }
+            _ => (found, 1),
+        };
+        let needed = if worse {
+            self.settings.degrade_after
+        } else {
+            self.settings.recover_after
+        };
+        if count < needed.max(1) {
+            self.pending = Some((target, count));
+            return None;
+        }
+
+        let change = TierChange {
+            from: self.tier,
+            to: target,
+            at,
+            capabilities,
+        };
+        self.pending = None;
+        self.tier = target;
+        self.status.since = Some(at);
+        self.status.transitions += 1;
+        Some(change)
+    }
+
+    /// Tier of the fleet and what the probes found.
+    #[must_use]
+    pub const fn status(&self) -> DegradationStatus {
+        DegradationStatus {
+            tier: self.tier,
+            ..self.status
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use chrono::{Duration, TimeZone};
+
+    use super::*;
+
+    const UP: Capabilities = Capabilities {
+        reads: true,
+        sends: true,
+        realtime: None,
+    };
+    const NO_SENDS: Capabilities = Capabilities {
+        reads: true,
+        sends: false,
+        realtime: None,
+    };
+    const DOWN: Capabilities = Capabilities {
+        reads: false,
+        sends: false,
+        realtime: None,
+    };
+
+    /// Record `probes` a minute apart, returning the tier after each.
+    fn walk(degradation: &mut Degradation, probes: &[Capabilities]) -> Vec<DegradationTier> {
+        let start = Utc.with_ymd_and_hms(2025, 1, 1, 12, 0, 0).unwrap();
+        let offset = i64::try_from(degradation.status().probes).unwrap();
+        probes
+            .iter()
+            .zip(offset..)
+            .map(|(capabilities, minute)| {
+                degradation.record(*capabilities, start + Duration::minutes(minute));
+                degradation.tier()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn tiers_change_only_after_probes_in_a_row() {
+        use DegradationTier::{Normal, Paused, ReadOnly};
+
+        let settings = DegradationSettings {
+            degrade_after: 2,
+            recover_after: 3,
+        };
+        let mut degradation = Degradation::new(settings);
+
+        // A single failed probe is forgotten
+        assert_eq!(
+            walk(&mut degradation, &[NO_SENDS, UP, NO_SENDS, NO_SENDS]),
+            [Normal, Normal, Normal, ReadOnly]
+        );
+        assert_eq!(
+            walk(&mut degradation, &[DOWN, DOWN, UP, DOWN]),
+            [ReadOnly, Paused, Paused, Paused]
+        );
+        // Recovering takes the longer run, only as far as all of it agrees
+        assert_eq!(
+            walk(&mut degradation, &[UP, UP, NO_SENDS, UP, UP, UP]),
+            [Paused, Paused, ReadOnly, ReadOnly, ReadOnly, Normal]
+        );
+
+        let status = degradation.status();
+        assert_eq!(status.tier, Normal);
+        assert_eq!((status.probes, status.transitions), (14, 4));
+        assert_eq!(status.capabilities, Some(UP));
+        assert_eq!(status.since, status.last_probe);
+    }
+
+    #[test]
+    fn mixed_failures_degrade_to_the_mildest_tier() {
+        let settings = DegradationSettings {
+            degrade_after: 3,
+            recover_after: 3,
+        };
+        let mut degradation = Degradation::new(settings);
+
+        walk(&mut degradation, &[DOWN, NO_SENDS]);
+        let change = degradation.record(DOWN, Utc::now()).unwrap();
+        assert_eq!(
+            (change.from, change.to),
+            (DegradationTier::Normal, DegradationTier::ReadOnly)
+        );
+        assert!(!change.is_recovery());
+    }
+
+    #[test]
+    fn endpoints_combine_to_the_best_of_them() {
+        assert_eq!(NO_SENDS.or(DOWN), NO_SENDS);
+        assert_eq!(DOWN.or(UP).tier(), DegradationTier::Normal);
+        assert_eq!(DOWN.or(DOWN).tier(), DegradationTier::Paused);
+
+        let realtime = |works| Capabilities {
+            realtime: Some(works),
+            ..UP
+        };
+        assert_eq!(realtime(false).or(realtime(true)), realtime(true));
+        assert_eq!(realtime(false).or(UP), realtime(false));
+    }
+}
@@ -28,13 +28,70 @@
 //! breaker.record_success("wallet_2");
 //! assert!(!breaker.is_tripped("wallet_2"));
 //! ```
+//!
+//! # Global Breaker
+//!
+//! The [`GlobalBreaker`] holds back the whole fleet when the RPC endpoint
+//! keeps rate limiting it. Rate limits are imposed per endpoint rather than
+//! per wallet, so backing off one wallet does not help while the others keep
+//! sending.
+//!
+//! ```
+//! use fleet_core::safety::GlobalBreaker;
+//! use std::time::Duration;
+//!
+//! let mut breaker = GlobalBreaker::new(3, Duration::from_secs(60), Duration::from_secs(300));
+//!
+//! breaker.record_rate_limited();
+//! breaker.record_rate_limited();
+//! assert!(!breaker.is_open());
+//!
+//! // The third hit within the window opens the breaker for everyone
+//! assert!(breaker.record_rate_limited());
+//! assert!(breaker.is_open());
+//! ```
+//!
+//! # Budgets
+//!
+//! The [`BudgetManager`] caps what wallets and the fleet as a whole spend in
+//! gas, DATA and actions per day and over their lifetime.
+//!
+//! ```
+//! use fleet_core::safety::{BudgetCaps, BudgetManager, Spend, SpendLimit};
+//!
+//! let daily = SpendLimit { actions: Some(1), ..SpendLimit::UNLIMITED };
+//! let mut budget = BudgetManager::new(BudgetCaps::default());
+//! budget.set_caps("wallet_1", BudgetCaps { daily, ..BudgetCaps::default() });
+//!
+//! assert!(budget.can_spend("wallet_1", &Spend::action()));
+//! budget.record_spend("wallet_1", &Spend::action());
+//! assert!(!budget.can_spend("wallet_1", &Spend::action()));
+//! ```
+//!
+//! # Degradation
+//!
+//! The [`Degradation`] moves the fleet to read-only or paused tiers while
+//! probes of its RPC endpoints find sends, or reads too, failing. Errors
+//! then say nothing about the wallets, so the service
+//! [freezes](CircuitBreaker::freeze) their breakers meanwhile.
 
-use std::collections::{HashMap, HashSet};
+mod budget;
+mod degradation;
+
+pub use budget::{BudgetCaps, BudgetManager, BudgetStatus, Spend, SpendLedger, SpendLimit};
+pub use degradation::{
+    Capabilities, Degradation, DegradationSettings, DegradationStatus, DegradationTier, TierChange,
+};
+
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use std::time::Duration;
 
 use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 use tracing::{info, warn};
 
+use crate::clock::{SharedClock, system_clock};
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // CIRCUIT BREAKER
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -78,6 +135,18 @@ pub struct CircuitBreaker {
 
     /// When each wallet was tripped (for auto-reset calculation).
     trip_times: HashMap<String, DateTime<Utc>>,
+
+    /// Times any wallet was tripped since creation.
+    total_trips: u64,
+
+    /// Times each wallet was tripped since creation.
+    trips: HashMap<String, u64>,
+
+    /// When the breaker was frozen, if it is.
+    frozen_at: Option<DateTime<Utc>>,
+
+    /// Source of the current time.
+    clock: SharedClock,
 }
 
 impl CircuitBreaker {
@@ -95,27 +164,43 @@ impl CircuitBreaker {
             error_counts: HashMap::new(),
             tripped: HashSet::new(),
             trip_times: HashMap::new(),
+            total_trips: 0,
+            trips: HashMap::new(),
+            frozen_at: None,
+            clock: system_clock(),
         }
     }
 
+    /// Use `clock` for trip times and cooldowns instead of the system clock.
+    #[must_use]
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
     /// Record a successful operation for a wallet.
     ///
-    /// Resets the error count for this wallet to zero.
+    /// Resets the error count for this wallet to zero, unless the breaker is
+    /// frozen.
     pub fn record_success(&mut self, wallet_id: &str) {
+        if self.is_frozen() {
+            return;
+        }
         self.error_counts.remove(wallet_id);
     }
 
     /// Record a failed operation for a wallet.
     ///
     /// Increments the error count. If the threshold is reached, trips the
-    /// circuit breaker for this wallet.
+    /// circuit breaker for this wallet. Errors while the breaker is frozen
+    /// are not counted.
     ///
     /// # Returns
     ///
     /// `true` if this error caused the circuit to trip, `false` otherwise.
     pub fn record_error(&mut self, wallet_id: &str) -> bool {
         // Already tripped - don't count more errors
-        if self.tripped.contains(wallet_id) {
+        if self.tripped.contains(wallet_id) || self.is_frozen() {
             return false;
         }
 
@@ -133,7 +218,9 @@ impl CircuitBreaker {
                 "Circuit breaker tripped"
             );
             self.tripped.insert(wallet_id.to_string());
-            self.trip_times.insert(wallet_id.to_string(), Utc::now());
+            self.trip_times.insert(wallet_id.to_string(), self.clock.now());
+            self.total_trips += 1;
+            *self.trips.entry(wallet_id.to_string()).or_default() += 1;
             return true;
         }
 
@@ -158,6 +245,21 @@ impl CircuitBreaker {
         self.tripped.len()
     }
 
+    /// Get how many times any wallet was tripped since creation.
+    ///
+    /// Unlike [`tripped_count`](Self::tripped_count) this includes wallets
+    /// that have since been reset.
+    #[must_use]
+    pub const fn total_trips(&self) -> u64 {
+        self.total_trips
+    }
+
+    /// Get how many times a wallet was tripped since creation.
+    #[must_use]
+    pub fn trips_of(&self, wallet_id: &str) -> u64 {
+        self.trips.get(wallet_id).copied().unwrap_or(0)
+    }
+
     /// Get all tripped wallet IDs.
     pub fn tripped_wallets(&self) -> impl Iterator<Item = &str> {
         self.tripped.iter().map(String::as_str)
@@ -185,11 +287,47 @@ impl CircuitBreaker {
         }
     }
 
+    /// Stop counting errors and successes, and stop the cooldowns of
+    /// tripped wallets, until [`thaw`](Self::thaw).
+    ///
+    /// For while errors are not the wallets' fault, such as an RPC outage:
+    /// counts and trips stay as they were, neither raised by the outage nor
+    /// cleared by it.
+    pub fn freeze(&mut self) {
+        if self.frozen_at.is_none() {
+            self.frozen_at = Some(self.clock.now());
+            info!(tripped = self.tripped.len(), "Circuit breakers frozen");
+        }
+    }
+
+    /// Count errors and successes again, resuming the cooldowns where
+    /// [`freeze`](Self::freeze) stopped them.
+    pub fn thaw(&mut self) {
+        let Some(frozen_at) = self.frozen_at.take() else {
+            return;
+        };
+        let frozen_for = self.clock.now() - frozen_at;
+        for time in self.trip_times.values_mut() {
+            *time += frozen_for;
+        }
+        info!(frozen_secs = frozen_for.num_seconds(), "Circuit breakers thawed");
+    }
+
+    /// Whether the breaker is frozen.
+    #[must_use]
+    pub const fn is_frozen(&self) -> bool {
+        self.frozen_at.is_some()
+    }
+
     /// Check and auto-reset wallets that have exceeded their cooldown.
     ///
-    /// Returns the number of wallets that were auto-reset.
+    /// Returns the number of wallets that were auto-reset, none while the
+    /// breaker is frozen.
     pub fn check_auto_reset(&mut self) -> usize {
-        let now = Utc::now();
+        if self.is_frozen() {
+            return 0;
+        }
+        let now = self.clock.now();
         let cooldown_chrono = chrono::Duration::from_std(self.cooldown)
             .unwrap_or_else(|e| {
                 warn!(
@@ -223,13 +361,16 @@ impl CircuitBreaker {
     }
 
     /// Get time remaining until auto-reset for a tripped wallet.
+    ///
+    /// While the breaker is frozen, this is the time that remained when it
+    /// froze.
     #[must_use]
     pub fn time_until_reset(&self, wallet_id: &str) -> Option<Duration> {
         let trip_time = self.trip_times.get(wallet_id)?;
         let cooldown_chrono = chrono::Duration::from_std(self.cooldown)
             .unwrap_or_else(|_| chrono::Duration::hours(1));
         let reset_at = *trip_time + cooldown_chrono;
-        let now = Utc::now();
+        let now = self.frozen_at.unwrap_or_else(|| self.clock.now());
 
         if now >= reset_at {
             Some(Duration::ZERO)
@@ -237,6 +378,231 @@ impl CircuitBreaker {
             (reset_at - now).to_std().ok()
         }
     }
+
+    /// Capture the breaker's state so it can outlive the process.
+    ///
+    /// The threshold and cooldown are configuration and not part of the
+    /// snapshot. A frozen breaker is captured as if thawed now, so its
+    /// cooldowns resume on restore.
+    #[must_use]
+    pub fn snapshot(&self) -> BreakerSnapshot {
+        let frozen_for = self
+            .frozen_at
+            .map_or_else(chrono::Duration::zero, |at| self.clock.now() - at);
+        BreakerSnapshot {
+            error_counts: self.error_counts.clone().into_iter().collect(),
+            trip_times: self
+                .tripped
+                .iter()
+                .filter_map(|id| Some((id.clone(), *self.trip_times.get(id)? + frozen_for)))
+                .collect(),
+            total_trips: self.total_trips,
+            trips: self.trips.clone().into_iter().collect(),
+        }
+    }
+
+    /// Restore state captured by [`snapshot`](Self::snapshot), replacing the
+    /// current state.
+    ///
+    /// Tripped wallets keep their original trip times, so their cooldowns
+    /// continue where they left off rather than restarting. Wallets whose
+    /// cooldown ran out in the meantime reset on the next
+    /// [`check_auto_reset`](Self::check_auto_reset).
+    pub fn restore(&mut self, snapshot: BreakerSnapshot) {
+        self.error_counts = snapshot.error_counts.into_iter().collect();
+        self.tripped = snapshot.trip_times.keys().cloned().collect();
+        self.trip_times = snapshot.trip_times.into_iter().collect();
+        self.total_trips = snapshot.total_trips;
+        self.trips = snapshot.trips.into_iter().collect();
+    }
+}
+
+/// Persistent state of a [`CircuitBreaker`].
+///
+/// # Example
+///
+/// ```
+/// use fleet_core::safety::CircuitBreaker;
+/// use std::time::Duration;
+///
+/// let mut breaker = CircuitBreaker::new(1, Duration::from_secs(3600));
+/// breaker.record_error("wallet_1");
+///
+/// let mut restarted = CircuitBreaker::new(1, Duration::from_secs(3600));
+/// restarted.restore(breaker.snapshot());
+/// assert!(restarted.is_tripped("wallet_1"));
+/// assert_eq!(restarted.trip_time("wallet_1"), breaker.trip_time("wallet_1"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BreakerSnapshot {
+    /// Consecutive error counts per wallet.
+    #[serde(default)]
+    pub error_counts: BTreeMap<String, u32>,
+
+    /// When each currently tripped wallet was tripped.
+    #[serde(default)]
+    pub trip_times: BTreeMap<String, DateTime<Utc>>,
+
+    /// Times any wallet was tripped.
+    #[serde(default)]
+    pub total_trips: u64,
+
+    /// Times each wallet was tripped.
+    #[serde(default)]
+    pub trips: BTreeMap<String, u64>,
+}
+
+impl BreakerSnapshot {
+    /// Keep only the state of wallets `keep` accepts, e.g. those still in
+    /// the fleet.
+    ///
+    /// The total trip count is kept as is.
+    #[must_use]
+    pub fn retain(mut self, mut keep: impl FnMut(&str) -> bool) -> Self {
+        self.error_counts.retain(|id, _| keep(id));
+        self.trip_times.retain(|id, _| keep(id));
+        self.trips.retain(|id, _| keep(id));
+        self
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// GLOBAL BREAKER
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Fleet-wide breaker fed by rate-limited errors.
+///
+/// Opens after `max_hits` rate-limited errors within `window` and stays open
+/// for `cooldown`, during which no wallet should act. Hits that led to a trip
+/// are forgotten, so the breaker needs a fresh burst to open again.
+///
+/// # Thread Safety
+///
+/// This struct is NOT thread-safe. Wrap in a `Mutex` or `RwLock` if
+/// concurrent access is needed.
+#[derive(Debug)]
+pub struct GlobalBreaker {
+    /// Rate-limited errors within the window that open the breaker.
+    max_hits: u32,
+
+    /// Window over which hits are counted.
+    window: Duration,
+
+    /// How long the breaker stays open.
+    cooldown: Duration,
+
+    /// Times of recent rate-limited errors, oldest first.
+    hits: VecDeque<DateTime<Utc>>,
+
+    /// When the breaker closes again, if it is open.
+    open_until: Option<DateTime<Utc>>,
+
+    /// Times the breaker opened since creation.
+    total_trips: u64,
+
+    /// Source of the current time.
+    clock: SharedClock,
+}
+
+impl GlobalBreaker {
+    /// Create a new global breaker.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_hits` - Rate-limited errors within `window` that open the breaker
+    /// * `window` - Window over which rate-limited errors are counted
+    /// * `cooldown` - How long the breaker stays open
+    #[must_use]
+    pub fn new(max_hits: u32, window: Duration, cooldown: Duration) -> Self {
+        Self {
+            max_hits,
+            window,
+            cooldown,
+            hits: VecDeque::new(),
+            open_until: None,
+            total_trips: 0,
+            clock: system_clock(),
+        }
+    }
+
+    /// Use `clock` for hit times and cooldowns instead of the system clock.
+    #[must_use]
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Record a rate-limited error.
+    ///
+    /// # Returns
+    ///
+    /// `true` if this error opened the breaker, `false` otherwise.
+    pub fn record_rate_limited(&mut self) -> bool {
+        let now = self.clock.now();
+        if self.open_until.is_some_and(|until| now < until) {
+            return false;
+        }
+
+        let window = to_chrono(self.window);
+        while self.hits.front().is_some_and(|hit| now - *hit > window) {
+            self.hits.pop_front();
+        }
+        self.hits.push_back(now);
+
+        if self.hits.len() < usize::try_from(self.max_hits).unwrap_or(usize::MAX) {
+            return false;
+        }
+
+        let until = now + to_chrono(self.cooldown);
+        warn!(
+            hits = self.hits.len(),
+            window_secs = self.window.as_secs(),
+            until = %until,
+            "Global breaker tripped by rate limiting"
+        );
+        self.hits.clear();
+        self.open_until = Some(until);
+        self.total_trips += 1;
+        true
+    }
+
+    /// Check if the breaker is open, i.e. no wallet should act.
+    #[must_use]
+    pub fn is_open(&self) -> bool {
+        self.open_until().is_some()
+    }
+
+    /// When the breaker closes again, if it is open.
+    #[must_use]
+    pub fn open_until(&self) -> Option<DateTime<Utc>> {
+        let now = self.clock.now();
+        self.open_until.filter(|until| now < *until)
+    }
+
+    /// Rate-limited errors counted toward the next trip.
+    #[must_use]
+    pub fn recent_hits(&self) -> usize {
+        self.hits.len()
+    }
+
+    /// Get how many times the breaker opened since creation.
+    #[must_use]
+    pub const fn total_trips(&self) -> u64 {
+        self.total_trips
+    }
+
+    /// Close the breaker and forget recent hits.
+    pub fn reset(&mut self) {
+        if self.open_until.take().is_some() {
+            info!("Global breaker manually reset");
+        }
+        self.hits.clear();
+    }
+}
+
+/// Convert a duration for date arithmetic, falling back to one hour if it is too large.
+fn to_chrono(duration: Duration) -> chrono::Duration {
+    chrono::Duration::from_std(duration).unwrap_or_else(|_| chrono::Duration::hours(1))
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -244,6 +610,7 @@ impl CircuitBreaker {
 // ═══════════════════════════════════════════════════════════════════════════════
 
 #[cfg(test)]
+#[allow(clippy::unwrap_used)]
 mod tests {
     use super::*;
 
@@ -328,6 +695,119 @@ mod tests {
         assert!(!breaker.is_tripped("wallet_1"));
     }
 
+    #[test]
+    fn auto_reset_follows_clock() {
+        use std::sync::Arc;
+
+        use crate::clock::VirtualClock;
+
+        let clock = Arc::new(VirtualClock::new(Utc::now()));
+        let mut breaker =
+            CircuitBreaker::new(1, Duration::from_secs(3600)).with_clock(Arc::clone(&clock) as _);
+
+        breaker.record_error("wallet_1");
+        assert_eq!(breaker.time_until_reset("wallet_1"), Some(Duration::from_secs(3600)));
+
+        clock.advance(chrono::Duration::minutes(61));
+        assert_eq!(breaker.check_auto_reset(), 1);
+
+        // Resets don't undo the trip count
+        breaker.record_error("wallet_1");
+        assert_eq!(breaker.tripped_count(), 1);
+        assert_eq!(breaker.total_trips(), 2);
+        assert_eq!((breaker.trips_of("wallet_1"), breaker.trips_of("wallet_2")), (2, 0));
+    }
+
+    #[test]
+    fn snapshot_keeps_cooldown_across_restart() {
+        use std::sync::Arc;
+
+        use crate::clock::VirtualClock;
+
+        let clock = Arc::new(VirtualClock::new(Utc::now()));
+        let mut breaker =
+            CircuitBreaker::new(1, Duration::from_secs(3600)).with_clock(Arc::clone(&clock) as _);
+        breaker.record_error("wallet_1");
+        clock.advance(chrono::Duration::minutes(20));
+        breaker.record_error("wallet_2");
+        breaker.record_success("wallet_2");
+
+        let saved = serde_json::to_string(&breaker.snapshot()).unwrap();
+
+        // Down for 10 minutes
+        clock.advance(chrono::Duration::minutes(10));
+        let mut restarted =
+            CircuitBreaker::new(1, Duration::from_secs(3600)).with_clock(Arc::clone(&clock) as _);
+        restarted.restore(serde_json::from_str(&saved).unwrap());
+
+        assert!(restarted.is_tripped("wallet_1"));
+        assert!(restarted.is_tripped("wallet_2"));
+        assert_eq!(restarted.total_trips(), 2);
+        assert_eq!(restarted.trips_of("wallet_1"), 1);
+
+        // The cooldown runs from the original trip, not the restart
+        let remaining = restarted.time_until_reset("wallet_1").unwrap();
+        assert!(remaining.abs_diff(Duration::from_secs(30 * 60)) <= Duration::from_secs(1));
+
+        clock.advance(chrono::Duration::minutes(31));
+        assert_eq!(restarted.check_auto_reset(), 1);
+        assert!(!restarted.is_tripped("wallet_1"));
+        assert!(restarted.is_tripped("wallet_2"));
+    }
+
+    #[test]
+    fn snapshot_defaults_missing_fields() {
+        let snapshot: BreakerSnapshot = serde_json::from_str("{}").unwrap();
+        assert_eq!(snapshot, BreakerSnapshot::default());
+
+        let mut breaker = CircuitBreaker::new(1, Duration::from_secs(3600));
+        breaker.record_error("wallet_1");
+        breaker.record_error("wallet_2");
+        let snapshot = breaker.snapshot().retain(|id| id != "wallet_2");
+
+        breaker.restore(snapshot);
+        assert!(breaker.is_tripped("wallet_1"));
+        assert!(!breaker.is_tripped("wallet_2"));
+        assert_eq!(breaker.total_trips(), 2);
+    }
+
+    #[test]
+    fn frozen_breaker_keeps_counts_and_cooldowns() {
+        use std::sync::Arc;
+
+        use crate::clock::VirtualClock;
+
+        let clock = Arc::new(VirtualClock::new(Utc::now()));
+        let mut breaker =
+            CircuitBreaker::new(2, Duration::from_secs(3600)).with_clock(Arc::clone(&clock) as _);
+        breaker.record_error("wallet_1");
+        breaker.record_error("wallet_1");
+        breaker.record_error("wallet_2");
+        clock.advance(chrono::Duration::minutes(40));
+
+        breaker.freeze();
+        assert!(breaker.is_frozen());
+        assert!(!breaker.record_error("wallet_2"));
+        breaker.record_success("wallet_2");
+        assert_eq!(breaker.error_count("wallet_2"), 1);
+
+        // The cooldown stands still while frozen
+        clock.advance(chrono::Duration::hours(2));
+        assert_eq!(breaker.check_auto_reset(), 0);
+        assert_eq!(breaker.time_until_reset("wallet_1"), Some(Duration::from_secs(20 * 60)));
+        let snapshot = breaker.snapshot();
+
+        breaker.thaw();
+        assert!(!breaker.is_frozen());
+        assert_eq!(breaker.time_until_reset("wallet_1"), Some(Duration::from_secs(20 * 60)));
+        assert_eq!(snapshot.trip_times.get("wallet_1"), breaker.trip_time("wallet_1").as_ref());
+        assert!(breaker.record_error("wallet_2"));
+
+        clock.advance(chrono::Duration::minutes(21));
+        assert_eq!(breaker.check_auto_reset(), 1);
+        assert!(!breaker.is_tripped("wallet_1"));
+    }
+
     #[test]
     fn tripped_wallets_iterator() {
         let mut breaker = CircuitBreaker::new(1, Duration::from_secs(3600));
@@ -342,4 +822,40 @@ mod tests {
         assert!(tripped.contains("wallet_2"));
         assert!(tripped.contains("wallet_3"));
     }
+
+    #[test]
+    fn global_breaker_opens_on_burst_of_rate_limits() {
+        use std::sync::Arc;
+
+        use crate::clock::{Clock, VirtualClock};
+
+        let clock = Arc::new(VirtualClock::new(Utc::now()));
+        let mut breaker =
+            GlobalBreaker::new(3, Duration::from_secs(60), Duration::from_secs(300))
+                .with_clock(Arc::clone(&clock) as _);
+
+        // Hits spread wider than the window never add up
+        for _ in 0..3 {
+            assert!(!breaker.record_rate_limited());
+            clock.advance(chrono::Duration::seconds(61));
+        }
+        assert!(!breaker.is_open());
+
+        // The last hit has left the window too, so it takes three more
+        assert!(!breaker.record_rate_limited());
+        assert!(!breaker.record_rate_limited());
+        assert_eq!(breaker.recent_hits(), 2);
+        assert!(breaker.record_rate_limited());
+        let until = clock.now() + chrono::Duration::seconds(300);
+        assert_eq!(breaker.open_until(), Some(until));
+
+        // While open, more hits neither count nor extend the cooldown
+        assert!(!breaker.record_rate_limited());
+        assert_eq!(breaker.open_until(), Some(until));
+
+        clock.advance(chrono::Duration::seconds(300));
+        assert!(!breaker.is_open());
+        assert_eq!(breaker.recent_hits(), 0);
+        assert_eq!(breaker.total_trips(), 1);
+    }
 }
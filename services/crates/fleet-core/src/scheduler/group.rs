@@ -0,0 +1,320 @@
+//! Group-level scheduling constraints.
+//!
+//! Wallets funded from the same source look related on chain. If many of
+//! them act at the same moment the correlation is obvious, so operators can
+//! tag wallets with a group and cap how many actions the group takes within a
+//! sliding window.
+
+use std::collections::{HashMap, VecDeque};
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// GROUP LIMIT
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Sliding-window cap on the actions of a wallet group.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GroupLimit {
+    /// Maximum actions the group may take within `window`.
+    pub max_actions: u32,
+
+    /// Length of the sliding window.
+    pub window: Duration,
+}
+
+impl GroupLimit {
+    /// Create a limit of `max_actions` per `window`.
+    #[must_use]
+    pub const fn new(max_actions: u32, window: Duration) -> Self {
+        Self {
+            max_actions,
+            window,
+        }
+    }
+}
+
+/// Usage of a group's limit at a point in time.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GroupStats {
+    /// Group name.
+    pub group: String,
+
+    /// Actions taken within the current window.
+    pub actions_in_window: u32,
+
+    /// Maximum actions allowed within the window.
+    pub max_actions: u32,
+
+    /// Length of the window in seconds.
+    pub window_secs: i64,
+
+    /// Fraction of the limit in use (0.0 - 1.0, above 1.0 if a lowered limit
+    /// is still exceeded).
+    pub utilization: f64,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// GROUP LIMITER
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Sliding-window action cap per wallet group.
+///
+/// An action at time `t` counts towards its group's limit while
+/// `now - t < window`; an action exactly one window old no longer counts.
+/// Actions are attributed to the group the wallet was in when it acted, so
+/// moving a wallet to another group at runtime neither carries its recent
+/// actions over nor counts them twice. Wallets without a group, and groups
+/// without a configured limit, are unconstrained.
+///
+/// # Example
+///
+/// ```
+/// use chrono::{Duration, Utc};
+/// use fleet_core::scheduler::{GroupLimit, GroupLimiter};
+///
+/// let mut limiter = GroupLimiter::new()
+///     .with_limit("funded_by_a", GroupLimit::new(2, Duration::minutes(5)));
+/// let now = Utc::now();
+///
+/// limiter.record_at(Some("funded_by_a"), "wallet_1", now);
+/// limiter.record_at(Some("funded_by_a"), "wallet_2", now);
+///
+/// // The third wallet of the group has to wait for the window to slide
+/// assert_eq!(
+///     limiter.available_at(Some("funded_by_a"), now),
+///     Some(now + Duration::minutes(5))
+/// );
+/// assert!(limiter.is_allowed_at(None, now));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct GroupLimiter {
+    /// Limit per group name.
+    limits: HashMap<String, GroupLimit>,
+
+    /// Timestamps and wallet IDs of recent actions per group, oldest first.
+    actions: HashMap<String, VecDeque<(DateTime<Utc>, String)>>,
+}
+
+impl GroupLimiter {
+    /// Create a limiter without any limits.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the limit of a group.
+    #[must_use]
+    pub fn with_limit(mut self, group: impl Into<String>, limit: GroupLimit) -> Self {
+        self.set_limit(group, limit);
+        self
+    }
+
+    /// Set or replace the limit of a group.
+    pub fn set_limit(&mut self, group: impl Into<String>, limit: GroupLimit) {
+        self.limits.insert(group.into(), limit);
+    }
+
+    /// Get the limit of a group.
+    #[must_use]
+    pub fn limit(&self, group: &str) -> Option<GroupLimit> {
+        self.limits.get(group).copied()
+    }
+
+    /// Check whether a wallet in `group` may act now.
+    #[must_use]
+    pub fn is_allowed(&self, group: Option<&str>) -> bool {
+        self.is_allowed_at(group, Utc::now())
+    }
+
+    /// Check whether a wallet in `group` may act at `now`.
+    #[must_use]
+    pub fn is_allowed_at(&self, group: Option<&str>, now: DateTime<Utc>) -> bool {
+        self.available_at(group, now).is_none()
+    }
+
+    /// When the next action of `group` will be allowed, if not at `now`.
+    ///
+    /// Returns `None` if a wallet in the group may act at `now`.
+    #[must_use]
+    pub fn available_at(&self, group: Option<&str>, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let group = group?;
+        let limit = self.limits.get(group)?;
+        let recent: Vec<_> = self.window(group, limit, now).collect();
+
+        let max = usize::try_from(limit.max_actions).unwrap_or(usize::MAX);
+        if recent.len() < max {
+            return None;
+        }
+
+        // Enough of the oldest actions have to leave the window to get below
+        // the limit again
+        let freeing = recent.len() - max;
+        Some(recent[freeing] + limit.window)
+    }
+
+    /// Record an action of `wallet_id` in `group` now.
+    pub fn record(&mut self, group: Option<&str>, wallet_id: &str) {
+        self.record_at(group, wallet_id, Utc::now());
+    }
+
+    /// Record an action of `wallet_id` in `group` at `at`.
+    ///
+    /// Actions of wallets without a group are not tracked.
+    pub fn record_at(&mut self, group: Option<&str>, wallet_id: &str, at: DateTime<Utc>) {
+        let Some(group) = group else {
+            return;
+        };
+
+        let actions = self.actions.entry(group.to_string()).or_default();
+        actions.push_back((at, wallet_id.to_string()));
+        actions.make_contiguous().sort_by_key(|(at, _)| *at);
+
+        // Prune entries that can no longer count towards any window
+        if let Some(limit) = self.limits.get(group) {
+            while actions
+                .front()
+                .is_some_and(|(oldest, _)| *oldest <= at - limit.window)
+            {
+                actions.pop_front();
+            }
+        }
+    }
+
+    /// Actions taken by a group within the window ending at `now`.
+    #[must_use]
+    pub fn actions_in_window(&self, group: &str, now: DateTime<Utc>) -> u32 {
+        let count = self
+            .limits
+            .get(group)
+            .map_or(0, |limit| self.window(group, limit, now).count());
+        u32::try_from(count).unwrap_or(u32::MAX)
+    }
+
+    /// Usage of every limited group at `now`, sorted by group name.
+    #[must_use]
+    pub fn stats_at(&self, now: DateTime<Utc>) -> Vec<GroupStats> {
+        let mut stats: Vec<_> = self
+            .limits
+            .iter()
+            .map(|(group, limit)| {
+                let actions_in_window = self.actions_in_window(group, now);
+                let utilization = if limit.max_actions == 0 {
+                    1.0
+                } else {
+                    f64::from(actions_in_window) / f64::from(limit.max_actions)
+                };
+                GroupStats {
+                    group: group.clone(),
+                    actions_in_window,
+                    max_actions: limit.max_actions,
+                    window_secs: limit.window.num_seconds(),
+                    utilization,
+                }
+            })
+            .collect();
+        stats.sort_by(|a, b| a.group.cmp(&b.group));
+        stats
+    }
+
+    /// Timestamps of a group's actions within the window ending at `now`,
+    /// oldest first.
+    fn window<'a>(
+        &'a self,
+        group: &str,
+        limit: &GroupLimit,
+        now: DateTime<Utc>,
+    ) -> impl Iterator<Item = DateTime<Utc>> + 'a {
+        let start = now - limit.window;
+        self.actions
+            .get(group)
+            .into_iter()
+            .flatten()
+            .map(|(at, _)| *at)
+            .filter(move |at| *at > start && *at <= now)
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limiter() -> GroupLimiter {
+        GroupLimiter::new()
+            .with_limit("a", GroupLimit::new(3, Duration::minutes(5)))
+            .with_limit("b", GroupLimit::new(1, Duration::minutes(1)))
+    }
+
+    #[test]
+    fn sliding_window_boundary() {
+        let mut limiter = limiter();
+        let start = Utc::now();
+        limiter.record_at(Some("a"), "w1", start);
+        limiter.record_at(Some("a"), "w2", start + Duration::minutes(1));
+        let third = start + Duration::minutes(2);
+        limiter.record_at(Some("a"), "w3", third);
+
+        assert!(!limiter.is_allowed_at(Some("a"), third));
+        assert_eq!(
+            limiter.available_at(Some("a"), third),
+            Some(start + Duration::minutes(5))
+        );
+
+        // Just inside the window the first action still counts...
+        let boundary = start + Duration::minutes(5);
+        assert!(!limiter.is_allowed_at(Some("a"), boundary - Duration::milliseconds(1)));
+        // ...and exactly one window later it no longer does
+        assert!(limiter.is_allowed_at(Some("a"), boundary));
+        assert_eq!(limiter.actions_in_window("a", boundary), 2);
+    }
+
+    #[test]
+    fn groups_are_independent() {
+        let mut limiter = limiter();
+        let now = Utc::now();
+
+        limiter.record_at(Some("b"), "w1", now);
+
+        assert!(!limiter.is_allowed_at(Some("b"), now));
+        assert!(limiter.is_allowed_at(Some("a"), now));
+        // No group, or a group without a limit, is unconstrained
+        limiter.record_at(Some("unlimited"), "w2", now);
+        assert!(limiter.is_allowed_at(Some("unlimited"), now));
+        assert!(limiter.is_allowed_at(None, now));
+    }
+
+    #[test]
+    fn changing_group_does_not_double_count() {
+        let mut limiter = limiter();
+        let now = Utc::now();
+
+        limiter.record_at(Some("a"), "w1", now);
+        // w1 moves to group b and acts again
+        limiter.record_at(Some("b"), "w1", now + Duration::seconds(1));
+
+        let later = now + Duration::seconds(2);
+        assert_eq!(limiter.actions_in_window("a", later), 1);
+        assert_eq!(limiter.actions_in_window("b", later), 1);
+    }
+
+    #[test]
+    fn stats_report_utilization() {
+        let mut limiter = limiter();
+        let now = Utc::now();
+        limiter.record_at(Some("a"), "w1", now);
+
+        let stats = limiter.stats_at(now);
+
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].group, "a");
+        assert_eq!(stats[0].actions_in_window, 1);
+        assert_eq!(stats[0].window_secs, 300);
+        assert!((stats[0].utilization - 1.0 / 3.0).abs() < 1e-9);
+        assert!(stats[1].utilization.abs() < 1e-9);
+    }
+}
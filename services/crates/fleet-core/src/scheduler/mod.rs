@@ -10,6 +10,9 @@
 //! - Random jitter to avoid patterns
 //! - Active hours consideration
 //! - AFK periods
+//! - Bursts of closely spaced actions, tracked per wallet
+//! - Group-level caps on correlated wallets ([`GroupLimiter`])
+//! - Ramping up wallets that are overdue after a restart ([`ColdStartRamp`])
 //!
 //! # Example
 //!
@@ -21,14 +24,24 @@
 //! let profile = BehaviorProfile::grinder();
 //!
 //! // Calculate next action time
-//! let next = scheduler.calculate_next_action(&profile);
+//! let next = scheduler.calculate_next_action("wallet_1", &profile);
 //! println!("Next action at: {}", next);
 //! ```
 
+mod group;
+mod ramp;
+
+use std::collections::HashMap;
+
 use chrono::{DateTime, Timelike, Utc};
 use rand::rngs::StdRng;
-use rand::SeedableRng;
+use rand::{Rng, SeedableRng};
+
+pub use group::{GroupLimit, GroupLimiter, GroupStats};
+pub use ramp::{ColdStartRamp, OverdueWallet, RampProgress, RampSettings};
 
+use crate::clock::{SharedClock, system_clock};
+use crate::plugins::FollowUpAction;
 use crate::profiles::BehaviorProfile;
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -38,11 +51,30 @@ use crate::profiles::BehaviorProfile;
 /// Scheduler for calculating action timing.
 ///
 /// Uses profile-based intervals with random jitter to create varied,
-/// natural-looking timing patterns.
+/// natural-looking timing patterns. Times are relative to the scheduler's
+/// [`Clock`](crate::clock::Clock), the system clock unless
+/// [replaced](Self::with_clock).
+///
+/// The scheduler remembers which wallets are in the middle of a burst (see
+/// [`BehaviorProfile::burst_probability`]), so it should be shared by all
+/// scheduling of a wallet.
+///
+/// A wallet [seeded](Self::seed_wallet) with its own seed draws its action
+/// intervals from its own RNG, so its jitter sequence does not depend on how
+/// many other wallets the scheduler serves or in which order.
 #[derive(Debug)]
 pub struct Scheduler {
     /// Random number generator for jitter.
     rng: StdRng,
+
+    /// Source of the current time.
+    clock: SharedClock,
+
+    /// Short-spaced actions left in each bursting wallet's burst.
+    bursts: HashMap<String, u32>,
+
+    /// RNGs of wallets with a seed of their own.
+    wallet_rngs: HashMap<String, StdRng>,
 }
 
 impl Scheduler {
@@ -51,6 +83,9 @@ impl Scheduler {
     pub fn new() -> Self {
         Self {
             rng: StdRng::from_os_rng(),
+            clock: system_clock(),
+            bursts: HashMap::new(),
+            wallet_rngs: HashMap::new(),
         }
     }
 
@@ -59,17 +94,113 @@ impl Scheduler {
     pub fn with_seed(seed: u64) -> Self {
         Self {
             rng: StdRng::seed_from_u64(seed),
+            clock: system_clock(),
+            bursts: HashMap::new(),
+            wallet_rngs: HashMap::new(),
         }
     }
 
-    /// Calculate the next action time based on a profile.
+    /// Use `clock` as the source of the current time.
+    #[must_use]
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Give a wallet its own RNG, seeded with `seed`.
+    ///
+    /// The wallet's action intervals then follow the same sequence for the
+    /// same seed, e.g. one derived from its address and last action time.
+    /// Seeding a wallet again restarts its sequence.
+    pub fn seed_wallet(&mut self, wallet_id: impl Into<String>, seed: u64) {
+        self.wallet_rngs
+            .insert(wallet_id.into(), StdRng::seed_from_u64(seed));
+    }
+
+    /// Calculate a wallet's next action time based on its profile.
     ///
     /// Returns a timestamp that is the current time plus a profile-based
-    /// interval with random jitter applied.
+    /// interval with random jitter applied. When a burst starts, the next
+    /// `burst_size` actions are spaced by the profile's burst spacing
+    /// instead, after which intervals return to normal.
+    #[must_use]
+    pub fn calculate_next_action(
+        &mut self,
+        wallet_id: &str,
+        profile: &BehaviorProfile,
+    ) -> DateTime<Utc> {
+        let bursting = self.continue_burst(wallet_id);
+        let rng = self.wallet_rngs.get_mut(wallet_id).unwrap_or(&mut self.rng);
+        let interval = if bursting {
+            profile.next_burst_interval(rng)
+        } else if let Some(size) = profile.maybe_start_burst(rng) {
+            // This spacing is the burst's first
+            if size > 1 {
+                self.bursts.insert(wallet_id.to_string(), size - 1);
+            }
+            profile.next_burst_interval(rng)
+        } else {
+            profile.next_interval(rng)
+        };
+        self.clock.now() + interval
+    }
+
+    /// Whether a wallet is in a burst.
     #[must_use]
-    pub fn calculate_next_action(&mut self, profile: &BehaviorProfile) -> DateTime<Utc> {
-        let interval = profile.next_interval(&mut self.rng);
-        Utc::now() + interval
+    pub fn is_bursting(&self, wallet_id: &str) -> bool {
+        self.bursts.contains_key(wallet_id)
+    }
+
+    /// End a wallet's burst, e.g. when it goes AFK.
+    pub fn end_burst(&mut self, wallet_id: &str) {
+        self.bursts.remove(wallet_id);
+    }
+
+    /// Use up one short-spaced action of a wallet's burst, if it is in one.
+    fn continue_burst(&mut self, wallet_id: &str) -> bool {
+        let Some(remaining) = self.bursts.get_mut(wallet_id) else {
+            return false;
+        };
+        *remaining -= 1;
+        if *remaining == 0 {
+            self.bursts.remove(wallet_id);
+        }
+        true
+    }
+
+    /// Calculate a retry time for a wallet that may not act before `earliest`.
+    ///
+    /// Adds 1-30 seconds of jitter so that wallets held back together don't
+    /// all retry at the same instant.
+    #[must_use]
+    pub fn delay_until(&mut self, earliest: DateTime<Utc>) -> DateTime<Utc> {
+        let jitter_ms = self.rng.random_range(1_000..=30_000);
+        earliest.max(self.clock.now()) + chrono::Duration::milliseconds(jitter_ms)
+    }
+
+    /// Calculate when a follow-up becomes due, a random delay from its range
+    /// after now.
+    #[must_use]
+    pub fn follow_up_at(&mut self, follow_up: &FollowUpAction) -> DateTime<Utc> {
+        let max = follow_up.max_delay_secs.max(follow_up.min_delay_secs);
+        let secs = self.rng.random_range(follow_up.min_delay_secs..=max);
+        let delay = i64::try_from(secs).map_or(chrono::Duration::MAX, chrono::Duration::seconds);
+        self.clock
+            .now()
+            .checked_add_signed(delay)
+            .unwrap_or(DateTime::<Utc>::MAX_UTC)
+    }
+
+    /// Plan the ramp-up of wallets that are overdue at startup, from now.
+    ///
+    /// Returns the ramp and the time each wallet is rescheduled to.
+    #[must_use]
+    pub fn plan_ramp(
+        &mut self,
+        settings: &RampSettings,
+        overdue: Vec<OverdueWallet>,
+    ) -> (ColdStartRamp, Vec<(String, DateTime<Utc>)>) {
+        ColdStartRamp::plan(settings, overdue, self.clock.now(), &mut self.rng)
     }
 
     /// Decide whether to go AFK based on profile probability.
@@ -78,7 +209,8 @@ impl Scheduler {
     /// is when the AFK period ends.
     #[must_use]
     pub fn maybe_go_afk(&mut self, profile: &BehaviorProfile) -> Option<DateTime<Utc>> {
-        profile.maybe_go_afk(&mut self.rng).map(|duration| Utc::now() + duration)
+        let now = self.clock.now();
+        profile.maybe_go_afk(&mut self.rng).map(|duration| now + duration)
     }
 
     /// Check if the current time is within active hours for a profile.
@@ -87,15 +219,20 @@ impl Scheduler {
     /// and off-hours probability.
     #[must_use]
     pub fn should_act_now(&mut self, profile: &BehaviorProfile) -> bool {
-        let hour = Self::current_hour();
+        let hour = Self::hour_of(self.clock.now());
         profile.should_act_now(hour, &mut self.rng)
     }
 
     /// Get the current hour in UTC (0-23).
     #[must_use]
-    #[allow(clippy::cast_possible_truncation)] // hour() returns 0-23, always fits in u8
     pub fn current_hour() -> u8 {
-        Utc::now().hour() as u8
+        Self::hour_of(Utc::now())
+    }
+
+    /// Get the hour of a time in UTC (0-23).
+    #[allow(clippy::cast_possible_truncation)] // hour() returns 0-23, always fits in u8
+    fn hour_of(time: DateTime<Utc>) -> u8 {
+        time.hour() as u8
     }
 }
 
@@ -119,7 +256,7 @@ mod tests {
         let profile = BehaviorProfile::grinder();
 
         let now = Utc::now();
-        let next = scheduler.calculate_next_action(&profile);
+        let next = scheduler.calculate_next_action("w", &profile);
 
         assert!(next > now, "next action should be in the future");
     }
@@ -130,7 +267,7 @@ mod tests {
 
         let whale_intervals: Vec<_> = (0..10)
             .map(|_| {
-                scheduler.calculate_next_action(&BehaviorProfile::whale())
+                scheduler.calculate_next_action("w", &BehaviorProfile::whale())
                     .signed_duration_since(Utc::now())
                     .num_seconds()
             })
@@ -138,7 +275,7 @@ mod tests {
 
         let degen_intervals: Vec<_> = (0..10)
             .map(|_| {
-                scheduler.calculate_next_action(&BehaviorProfile::degen())
+                scheduler.calculate_next_action("w", &BehaviorProfile::degen())
                     .signed_duration_since(Utc::now())
                     .num_seconds()
             })
@@ -162,8 +299,8 @@ mod tests {
         let mut sched2 = Scheduler::with_seed(42);
 
         for _ in 0..5 {
-            let t1 = sched1.calculate_next_action(&profile);
-            let t2 = sched2.calculate_next_action(&profile);
+            let t1 = sched1.calculate_next_action("w", &profile);
+            let t2 = sched2.calculate_next_action("w", &profile);
 
             // Times should be very close (within 1 second of each other
             // accounting for execution time)
@@ -171,4 +308,155 @@ mod tests {
             assert!(diff <= 1, "seeded schedulers should produce same results");
         }
     }
+
+    #[test]
+    fn seeded_wallets_keep_their_own_sequence() {
+        use std::sync::Arc;
+
+        use crate::clock::VirtualClock;
+
+        let profile = BehaviorProfile::degen();
+        let clock = Arc::new(VirtualClock::new(Utc::now()));
+        let intervals = |others: usize, shared_seed: u64| {
+            let mut scheduler = Scheduler::with_seed(shared_seed).with_clock(clock.clone());
+            scheduler.seed_wallet("w", 99);
+            (0..10)
+                .map(|_| {
+                    // Unseeded wallets draw from the shared RNG in between
+                    for i in 0..others {
+                        let _ = scheduler.calculate_next_action(&format!("other_{i}"), &profile);
+                    }
+                    scheduler.calculate_next_action("w", &profile)
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // Same sequence with other wallets in between and another shared seed
+        assert_eq!(intervals(0, 1), intervals(3, 2));
+
+        let mut scheduler = Scheduler::with_seed(1).with_clock(clock.clone());
+        scheduler.seed_wallet("w", 100);
+        let other: Vec<_> = (0..10)
+            .map(|_| scheduler.calculate_next_action("w", &profile))
+            .collect();
+        assert_ne!(other, intervals(0, 1));
+    }
+
+    #[test]
+    fn delay_until_adds_jitter() {
+        let mut scheduler = Scheduler::with_seed(42);
+        let earliest = Utc::now() + chrono::Duration::minutes(2);
+
+        for _ in 0..20 {
+            let retry = scheduler.delay_until(earliest);
+            assert!(retry > earliest);
+            assert!(retry <= earliest + chrono::Duration::seconds(30));
+        }
+    }
+
+    #[test]
+    fn follow_ups_are_due_within_their_delay() {
+        use crate::plugins::FollowUpAction;
+
+        let mut scheduler = Scheduler::with_seed(42);
+        let follow_up = FollowUpAction::new("p.stake", "Stake").with_delay_secs(60, 120);
+        for _ in 0..20 {
+            let delay = scheduler.follow_up_at(&follow_up) - Utc::now();
+            assert!(delay <= chrono::Duration::seconds(120));
+            assert!(delay > chrono::Duration::seconds(59));
+        }
+    }
+
+    #[test]
+    fn uses_injected_clock() {
+        use std::sync::Arc;
+
+        use chrono::TimeZone;
+
+        use crate::clock::VirtualClock;
+
+        let start = Utc.with_ymd_and_hms(2025, 1, 1, 3, 0, 0).single().unwrap_or_default();
+        let profile = BehaviorProfile::grinder();
+        let mut scheduler =
+            Scheduler::with_seed(42).with_clock(Arc::new(VirtualClock::new(start)));
+
+        let next = scheduler.calculate_next_action("w", &profile);
+
+        // Relative to the virtual time, not the wall clock
+        assert!(next > start);
+        assert!(next < start + chrono::Duration::days(1));
+    }
+
+    /// Inter-action times of `count` actions of one wallet, in seconds.
+    fn intervals(profile: &BehaviorProfile, count: usize) -> Vec<i64> {
+        use std::sync::Arc;
+
+        use chrono::TimeZone;
+
+        use crate::clock::VirtualClock;
+
+        let start = Utc.with_ymd_and_hms(2025, 1, 1, 12, 0, 0).single().unwrap_or_default();
+        let mut scheduler =
+            Scheduler::with_seed(7).with_clock(Arc::new(VirtualClock::new(start)));
+        (0..count)
+            .map(|_| (scheduler.calculate_next_action("w", profile) - start).num_seconds())
+            .collect()
+    }
+
+    /// Number of modes of a histogram: runs of populated buckets separated
+    /// by empty ones.
+    fn modes(intervals: &[i64], bucket_secs: i64) -> usize {
+        let mut histogram = [0_u32; 64];
+        for interval in intervals {
+            let bucket = usize::try_from(interval / bucket_secs).unwrap_or(0).min(63);
+            histogram[bucket] += 1;
+        }
+        histogram
+            .windows(2)
+            .filter(|pair| pair[0] == 0 && pair[1] > 0)
+            .count()
+            + usize::from(histogram[0] > 0)
+    }
+
+    #[test]
+    fn bursts_make_intervals_bimodal() {
+        // Sniper: 600s +/- 40% normally, 90s +/- 40% in bursts
+        let sniper = BehaviorProfile::sniper();
+        let bursting = intervals(&sniper, 10_000);
+        assert_eq!(modes(&bursting, 60), 2, "short and normal spacing");
+
+        let short = bursting.iter().filter(|secs| **secs < 300).count();
+        assert!(short > 3_000 && short < 8_000, "{short} of 10000 intervals in bursts");
+
+        let calm = BehaviorProfile {
+            burst_probability: 0.0,
+            ..sniper
+        };
+        let even = intervals(&calm, 10_000);
+        assert_eq!(modes(&even, 60), 1);
+        assert!(even.iter().all(|secs| *secs >= 360));
+    }
+
+    #[test]
+    fn bursts_are_per_wallet() {
+        let bursty = BehaviorProfile {
+            burst_probability: 1.0,
+            burst_size_range: 3..=3,
+            ..BehaviorProfile::sniper()
+        };
+        let mut scheduler = Scheduler::with_seed(42);
+
+        let _ = scheduler.calculate_next_action("a", &bursty);
+        assert!(scheduler.is_bursting("a"));
+        assert!(!scheduler.is_bursting("b"));
+
+        // Two more short spacings, then a new burst starts
+        let _ = scheduler.calculate_next_action("a", &BehaviorProfile::whale());
+        let _ = scheduler.calculate_next_action("a", &BehaviorProfile::whale());
+        assert!(!scheduler.is_bursting("a"));
+
+        let _ = scheduler.calculate_next_action("a", &bursty);
+        scheduler.end_burst("a");
+        assert!(!scheduler.is_bursting("a"));
+    }
 }
@@ -0,0 +1,405 @@
+//! Cold-start ramp of overdue wallets.
+//!
+//! After a restart, every wallet whose next action passed during the downtime
+//! is due at once. Left alone, the whole fleet acts within the first minute:
+//! a load spike on the endpoint and an obvious pattern on chain. A
+//! [`ColdStartRamp`] spreads the overdue wallets' first actions over a window
+//! instead, the most overdue and most active wallets tending to go first, and
+//! meters the state reads of their first cycle through a token bucket.
+
+// Allow precision loss for ordering weights (seconds, far below 2^52)
+#![allow(clippy::cast_precision_loss)]
+
+use std::collections::HashSet;
+
+use chrono::{DateTime, Duration, Utc};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// SETTINGS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// How overdue wallets are ramped up after a restart.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RampSettings {
+    /// Window the overdue wallets' first actions are spread over.
+    pub window: Duration,
+
+    /// Most first actions started within one minute of the ramp. The window
+    /// is stretched if it cannot fit every overdue wallet otherwise.
+    pub max_per_minute: u32,
+
+    /// State reads of the wallets' first cycle allowed per second, 0 for no
+    /// limit.
+    pub reads_per_sec: u32,
+}
+
+impl Default for RampSettings {
+    fn default() -> Self {
+        Self {
+            window: Duration::minutes(30),
+            max_per_minute: 20,
+            reads_per_sec: 5,
+        }
+    }
+}
+
+/// A wallet whose next action passed while the fleet was down.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OverdueWallet {
+    /// Wallet ID.
+    pub id: String,
+
+    /// How long ago the wallet's next action was due.
+    pub overdue: Duration,
+
+    /// Activity level of the wallet's profile, in actions per hour.
+    pub activity_level: f64,
+}
+
+/// How far a ramp has got.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RampProgress {
+    /// Overdue wallets being ramped up.
+    pub wallets: usize,
+
+    /// Wallets that started their first cycle.
+    pub started: usize,
+
+    /// When the last of the wallets is scheduled.
+    pub ends_at: DateTime<Utc>,
+}
+
+impl RampProgress {
+    /// Percentage of the wallets that started, 100 for an empty ramp.
+    #[must_use]
+    pub const fn percent(&self) -> usize {
+        if self.wallets == 0 {
+            return 100;
+        }
+        self.started * 100 / self.wallets
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// READ BUCKET
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Token bucket of state reads, refilled at a steady rate up to one second's
+/// worth.
+#[derive(Debug, Clone)]
+struct ReadBucket {
+    /// Reads per second, 0 for no limit.
+    rate: u32,
+
+    /// Reads available.
+    tokens: f64,
+
+    /// When `tokens` was last brought up to date.
+    updated_at: DateTime<Utc>,
+}
+
+impl ReadBucket {
+    /// A full bucket of `rate` reads per second.
+    fn new(rate: u32, now: DateTime<Utc>) -> Self {
+        Self {
+            rate,
+            tokens: f64::from(rate),
+            updated_at: now,
+        }
+    }
+
+    /// Take a read at `now`, or tell when the next one is available.
+    fn take_at(&mut self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        if self.rate == 0 {
+            return None;
+        }
+        let rate = f64::from(self.rate);
+        let elapsed = (now - self.updated_at).num_milliseconds().max(0) as f64 / 1000.0;
+        self.tokens = elapsed.mul_add(rate, self.tokens).min(rate);
+        self.updated_at = now.max(self.updated_at);
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            return None;
+        }
+        #[allow(clippy::cast_possible_truncation)] // At most 1000 ms
+        let wait_ms = ((1.0 - self.tokens) / rate * 1000.0).ceil() as i64;
+        Some(self.updated_at + Duration::milliseconds(wait_ms.max(1)))
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// RAMP
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// The ramp-up of the wallets that were overdue at startup.
+///
+/// [`plan`](Self::plan) orders the wallets by a weighted draw, where a
+/// wallet's weight is how long it is overdue times its profile's activity
+/// level, and deals them out over the minutes of the window, at most
+/// `max_per_minute` to a minute and at a random second within it. Until a
+/// wallet [started](Self::start) its first cycle, its state reads go through
+/// the ramp's [read bucket](Self::read_available_at).
+///
+/// # Example
+///
+/// ```
+/// use chrono::{Duration, Utc};
+/// use fleet_core::scheduler::{ColdStartRamp, OverdueWallet, RampSettings};
+/// use rand::SeedableRng;
+/// use rand::rngs::StdRng;
+///
+/// let now = Utc::now();
+/// let wallets: Vec<_> = (0..100)
+///     .map(|i| OverdueWallet {
+///         id: format!("wallet_{i}"),
+///         overdue: Duration::hours(1),
+///         activity_level: 5.0,
+///     })
+///     .collect();
+/// let (ramp, schedule) =
+///     ColdStartRamp::plan(&RampSettings::default(), wallets, now, &mut StdRng::seed_from_u64(7));
+///
+/// assert_eq!(schedule.len(), 100);
+/// assert!(schedule.iter().all(|(_, at)| *at >= now && *at < now + Duration::minutes(30)));
+/// assert_eq!(ramp.progress().started, 0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ColdStartRamp {
+    /// Wallets that have not started their first cycle yet.
+    pending: HashSet<String>,
+
+    /// Wallets being ramped up.
+    wallets: usize,
+
+    /// When the last of the wallets is scheduled.
+    ends_at: DateTime<Utc>,
+
+    /// First-cycle state reads.
+    reads: ReadBucket,
+}
+
+impl ColdStartRamp {
+    /// Plan the ramp of `overdue` wallets starting at `now`.
+    ///
+    /// Returns the ramp and the time each wallet is rescheduled to, in
+    /// ramp order.
+    #[must_use]
+    pub fn plan(
+        settings: &RampSettings,
+        overdue: Vec<OverdueWallet>,
+        now: DateTime<Utc>,
+        rng: &mut impl Rng,
+    ) -> (Self, Vec<(String, DateTime<Utc>)>) {
+        // Exponential race: the smallest key wins, and heavier wallets draw
+        // smaller keys
+        let mut keyed: Vec<_> = overdue
+            .into_iter()
+            .map(|wallet| {
+                let overdue_secs = wallet.overdue.num_seconds().max(1) as f64;
+                let weight = overdue_secs * wallet.activity_level.max(0.01);
+                let draw: f64 = 1.0 - rng.random::<f64>();
+                (-draw.ln() / weight, wallet.id)
+            })
+            .collect();
+        keyed.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let count = keyed.len();
+        let per_minute = usize::try_from(settings.max_per_minute.max(1)).unwrap_or(usize::MAX);
+        let window_minutes = usize::try_from(settings.window.num_minutes().max(1)).unwrap_or(1);
+        let minutes = window_minutes.max(count.div_ceil(per_minute));
+
+        let schedule: Vec<_> = keyed
+            .into_iter()
+            .enumerate()
+            .map(|(i, (_, id))| {
+                // Spread evenly over the minutes, never more than per_minute
+                // to one since minutes * per_minute >= count
+                let minute = i64::try_from(i * minutes / count).unwrap_or(i64::MAX);
+                let offset = Duration::minutes(minute)
+                    + Duration::milliseconds(rng.random_range(0..60_000));
+                (id, now + offset)
+            })
+            .collect();
+
+        let ramp = Self {
+            pending: schedule.iter().map(|(id, _)| id.clone()).collect(),
+            wallets: count,
+            ends_at: schedule.iter().map(|(_, at)| *at).max().unwrap_or(now),
+            reads: ReadBucket::new(settings.reads_per_sec, now),
+        };
+        (ramp, schedule)
+    }
+
+    /// Whether a wallet has yet to start its first cycle.
+    #[must_use]
+    pub fn is_pending(&self, wallet_id: &str) -> bool {
+        self.pending.contains(wallet_id)
+    }
+
+    /// Take a first-cycle state read at `now`, or tell when the next one is
+    /// available.
+    ///
+    /// Returns `None` if the read may go ahead.
+    pub fn read_available_at(&mut self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        self.reads.take_at(now)
+    }
+
+    /// Mark a wallet as having started its first cycle.
+    ///
+    /// Returns `true` if it was pending.
+    pub fn start(&mut self, wallet_id: &str) -> bool {
+        self.pending.remove(wallet_id)
+    }
+
+    /// Whether every wallet started, or the last was due before `now`.
+    ///
+    /// Wallets held back past the end of the ramp, e.g. by a tripped
+    /// breaker, then start as they would have without it.
+    #[must_use]
+    pub fn is_over_at(&self, now: DateTime<Utc>) -> bool {
+        self.pending.is_empty() || now > self.ends_at
+    }
+
+    /// How far the ramp has got.
+    #[must_use]
+    pub fn progress(&self) -> RampProgress {
+        RampProgress {
+            wallets: self.wallets,
+            started: self.wallets - self.pending.len(),
+            ends_at: self.ends_at,
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::collections::HashMap;
+
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    use super::*;
+
+    fn overdue(count: usize, activity_level: f64) -> Vec<OverdueWallet> {
+        (0..count)
+            .map(|i| OverdueWallet {
+                id: format!("w{i}"),
+                overdue: Duration::minutes(10),
+                activity_level,
+            })
+            .collect()
+    }
+
+    fn per_minute(schedule: &[(String, DateTime<Utc>)], start: DateTime<Utc>) -> Vec<usize> {
+        let mut counts: HashMap<i64, usize> = HashMap::new();
+        for (_, at) in schedule {
+            *counts.entry((*at - start).num_minutes()).or_default() += 1;
+        }
+        let mut counts: Vec<_> = counts.into_iter().collect();
+        counts.sort_unstable();
+        counts.into_iter().map(|(_, count)| count).collect()
+    }
+
+    #[test]
+    fn spreads_wallets_over_the_window() {
+        let now = Utc::now();
+        let mut rng = StdRng::seed_from_u64(1);
+        let (ramp, schedule) =
+            ColdStartRamp::plan(&RampSettings::default(), overdue(200, 5.0), now, &mut rng);
+
+        assert_eq!(schedule.len(), 200);
+        let counts = per_minute(&schedule, now);
+        assert_eq!(counts.len(), 30, "every minute of the window is used");
+        assert!(counts.iter().all(|count| *count <= 7), "{counts:?}");
+        assert!(ramp.progress().ends_at < now + Duration::minutes(30));
+    }
+
+    #[test]
+    fn stretches_the_window_to_the_per_minute_cap() {
+        let now = Utc::now();
+        let settings = RampSettings {
+            window: Duration::minutes(5),
+            max_per_minute: 10,
+            reads_per_sec: 0,
+        };
+        let mut rng = StdRng::seed_from_u64(2);
+        let (ramp, schedule) = ColdStartRamp::plan(&settings, overdue(95, 5.0), now, &mut rng);
+
+        let counts = per_minute(&schedule, now);
+        assert_eq!(counts.len(), 10);
+        assert!(counts.iter().all(|count| *count <= 10), "{counts:?}");
+        assert!(ramp.progress().ends_at >= now + Duration::minutes(9));
+    }
+
+    #[test]
+    fn overdue_and_active_wallets_tend_to_go_first() {
+        let now = Utc::now();
+        let mut wallets = overdue(100, 1.0);
+        for wallet in wallets.iter_mut().take(10) {
+            wallet.overdue = Duration::hours(10);
+        }
+        for wallet in wallets.iter_mut().skip(10).take(10) {
+            wallet.activity_level = 60.0;
+        }
+        let mut rng = StdRng::seed_from_u64(3);
+        let (_, schedule) = ColdStartRamp::plan(&RampSettings::default(), wallets, now, &mut rng);
+
+        let first: Vec<_> = schedule.iter().take(25).map(|(id, _)| id.as_str()).collect();
+        let heavy = first
+            .iter()
+            .filter(|id| id[1..].parse::<usize>().unwrap() < 20)
+            .count();
+        assert!(heavy >= 15, "{first:?}");
+    }
+
+    #[test]
+    fn tracks_progress() {
+        let now = Utc::now();
+        let mut rng = StdRng::seed_from_u64(4);
+        let (mut ramp, _) =
+            ColdStartRamp::plan(&RampSettings::default(), overdue(4, 5.0), now, &mut rng);
+
+        assert!(ramp.is_pending("w0"));
+        assert!(ramp.start("w0"));
+        assert!(!ramp.start("w0"));
+        assert!(!ramp.is_pending("w0"));
+        assert_eq!(ramp.progress().started, 1);
+        assert_eq!(ramp.progress().percent(), 25);
+        assert!(!ramp.is_over_at(now));
+
+        for id in ["w1", "w2", "w3"] {
+            ramp.start(id);
+        }
+        assert!(ramp.is_over_at(now));
+    }
+
+    #[test]
+    fn meters_first_cycle_reads() {
+        let now = Utc::now();
+        let settings = RampSettings {
+            reads_per_sec: 2,
+            ..RampSettings::default()
+        };
+        let mut rng = StdRng::seed_from_u64(5);
+        let (mut ramp, _) = ColdStartRamp::plan(&settings, overdue(10, 5.0), now, &mut rng);
+
+        assert_eq!(ramp.read_available_at(now), None);
+        assert_eq!(ramp.read_available_at(now), None);
+        let next = ramp.read_available_at(now).unwrap();
+        assert_eq!(next, now + Duration::milliseconds(500));
+        assert_eq!(ramp.read_available_at(next), None);
+
+        // Refills up to one second's worth
+        let later = now + Duration::minutes(1);
+        assert_eq!(ramp.read_available_at(later), None);
+        assert_eq!(ramp.read_available_at(later), None);
+        assert!(ramp.read_available_at(later).is_some());
+    }
+}
@@ -0,0 +1,244 @@
+//! Configuration validation reports.
+//!
+//! Validation collects every problem it finds into a [`ConfigReport`]
+//! instead of stopping at the first, so an operator can fix a config in one
+//! pass. Each [`ConfigIssue`] names the offending key by its dotted path in
+//! the config file, e.g. `wallets[3].key_source` or
+//! `plugins.ghostnet.max_boost_reward_share`.
+//!
+//! Errors make a config unusable; warnings point out settings that work but
+//! are probably not what was meant, such as running without any budget caps.
+//!
+//! ```
+//! use fleet_core::validation::{ConfigReport, Severity};
+//!
+//! let mut report = ConfigReport::new();
+//! report.error("wallets[3].key_source", "mnemonic 'fleet' not found in [mnemonics]");
+//! report.warning("safety.budget", "no budget caps are set");
+//!
+//! assert!(report.has_errors());
+//! assert_eq!(report.issues()[1].severity, Severity::Warning);
+//! ```
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// ISSUES
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// How bad a [`ConfigIssue`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    /// Works, but is probably a mistake.
+    Warning,
+    /// The config cannot be used.
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Warning => "warning",
+            Self::Error => "error",
+        })
+    }
+}
+
+/// One problem with a config key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConfigIssue {
+    /// Dotted path of the key, e.g. `wallets[3].key_source`.
+    pub path: String,
+
+    /// What is wrong with it.
+    pub message: String,
+
+    /// Whether the config is still usable.
+    pub severity: Severity,
+}
+
+impl ConfigIssue {
+    /// The issue with `prefix` put in front of its path, for keys of a
+    /// nested table.
+    #[must_use]
+    pub fn under(mut self, prefix: &str) -> Self {
+        self.path = join(prefix, &self.path);
+        self
+    }
+}
+
+impl fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+/// `path` under `prefix`; array indices and quoted keys attach directly.
+fn join(prefix: &str, path: &str) -> String {
+    if prefix.is_empty() {
+        path.to_string()
+    } else if path.is_empty() || path.starts_with('[') {
+        format!("{prefix}{path}")
+    } else {
+        format!("{prefix}.{path}")
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// REPORT
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Every issue validation found, in the order it found them.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ConfigReport {
+    issues: Vec<ConfigIssue>,
+}
+
+impl ConfigReport {
+    /// Create an empty report.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { issues: Vec::new() }
+    }
+
+    /// Record an error at `path`.
+    pub fn error(&mut self, path: impl Into<String>, message: impl Into<String>) {
+        self.push(path.into(), message.into(), Severity::Error);
+    }
+
+    /// Record a warning at `path`.
+    pub fn warning(&mut self, path: impl Into<String>, message: impl Into<String>) {
+        self.push(path.into(), message.into(), Severity::Warning);
+    }
+
+    fn push(&mut self, path: String, message: String, severity: Severity) {
+        self.issues.push(ConfigIssue {
+            path,
+            message,
+            severity,
+        });
+    }
+
+    /// Add the issues of `other`, found in the table at `prefix`.
+    pub fn extend_under(&mut self, prefix: &str, other: Self) {
+        self.issues
+            .extend(other.issues.into_iter().map(|issue| issue.under(prefix)));
+    }
+
+    /// All issues.
+    #[must_use]
+    pub fn issues(&self) -> &[ConfigIssue] {
+        &self.issues
+    }
+
+    /// The issues that make the config unusable.
+    pub fn errors(&self) -> impl Iterator<Item = &ConfigIssue> {
+        self.issues.iter().filter(|issue| issue.severity == Severity::Error)
+    }
+
+    /// The issues that are probably mistakes.
+    pub fn warnings(&self) -> impl Iterator<Item = &ConfigIssue> {
+        self.issues.iter().filter(|issue| issue.severity == Severity::Warning)
+    }
+
+    /// Check whether any issue is an error.
+    #[must_use]
+    pub fn has_errors(&self) -> bool {
+        self.errors().next().is_some()
+    }
+
+    /// Check whether validation found nothing.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+impl Extend<ConfigIssue> for ConfigReport {
+    fn extend<I: IntoIterator<Item = ConfigIssue>>(&mut self, issues: I) {
+        self.issues.extend(issues);
+    }
+}
+
+impl IntoIterator for ConfigReport {
+    type Item = ConfigIssue;
+    type IntoIter = std::vec::IntoIter<ConfigIssue>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.issues.into_iter()
+    }
+}
+
+/// A table of the issues, errors first:
+///
+/// ```text
+/// SEVERITY  PATH                    MESSAGE
+/// error     wallets[3].key_source   mnemonic 'fleet' not found in [mnemonics]
+/// warning   safety.budget           no budget caps are set
+/// ```
+impl fmt::Display for ConfigReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self
+            .issues
+            .iter()
+            .map(|issue| issue.path.len())
+            .chain(std::iter::once("PATH".len()))
+            .max()
+            .unwrap_or_default();
+        write!(f, "{:<9} {:<width$}  MESSAGE", "SEVERITY", "PATH")?;
+        for issue in self.errors().chain(self.warnings()) {
+            let severity = issue.severity.to_string();
+            write!(f, "\n{severity:<9} {:<width$}  {}", issue.path, issue.message)?;
+        }
+        Ok(())
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nested_issues_keep_their_path() {
+        let mut chain = ConfigReport::new();
+        chain.error("chain_id", "must be > 0");
+        let mut wallets = ConfigReport::new();
+        wallets.warning("[0].private_key", "raw keys are for development only");
+
+        let mut report = ConfigReport::new();
+        report.extend_under("chains.testnet", chain);
+        report.extend_under("wallets", wallets);
+
+        let paths: Vec<_> = report.issues().iter().map(|issue| issue.path.as_str()).collect();
+        assert_eq!(paths, ["chains.testnet.chain_id", "wallets[0].private_key"]);
+        assert_eq!((report.errors().count(), report.warnings().count()), (1, 1));
+    }
+
+    #[test]
+    fn table_lists_errors_first() {
+        let mut report = ConfigReport::new();
+        assert!(!report.has_errors());
+        report.warning("safety.budget", "no budget caps are set");
+        report.error("wallets[3].key_source", "mnemonic 'fleet' not found");
+
+        let table = report.to_string();
+        let lines: Vec<_> = table.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("SEVERITY  PATH"));
+        assert!(lines[1].starts_with("error     wallets[3].key_source  mnemonic"));
+        assert!(lines[2].starts_with("warning   safety.budget"));
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json[1]["path"], "wallets[3].key_source");
+        assert_eq!(json[1]["severity"], "error");
+    }
+}
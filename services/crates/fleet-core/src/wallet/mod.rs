@@ -8,11 +8,14 @@
 //! The core type is [`WalletState`], which tracks everything needed for
 //! a managed wallet:
 //!
-//! - Native and token balances
+//! - Native and token balances, with the block and time each token balance
+//!   was read at (see [`TrackedBalance`])
 //! - Transaction nonce
 //! - Plugin-specific state (e.g., protocol positions)
 //! - Timing (last action, next scheduled action)
 //! - Health (active, error count, AFK status)
+//! - Warm-up progress of new wallets (see [`WarmupPlan`])
+//! - Retirement of wallets that are wound down (see [`Retirement`])
 //!
 //! # Example
 //!
@@ -36,7 +39,20 @@
 //!     // Ready for action
 //! }
 //! ```
+//!
+//! # Balance Freshness
+//!
+//! Decisions that size amounts from a token balance should check
+//! [`WalletState::is_balance_stale`] first. [`BalanceRefresher`] re-reads
+//! stale balances for a batch of wallets, using multicall when the provider
+//! supports it.
 
+mod refresher;
+mod retirement;
 mod state;
+mod warmup;
 
-pub use state::WalletState;
+pub use refresher::{BalanceRefresher, RefreshReport};
+pub use retirement::{Retirement, RetirementSettings, RetirementStage};
+pub use state::{PluginState, TrackedBalance, WalletState, decode_plugin_state};
+pub use warmup::{WarmupPlan, WarmupSettings, WarmupStep, WarmupStepKind};
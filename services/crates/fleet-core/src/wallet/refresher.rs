@@ -0,0 +1,417 @@
+//! Refreshing stale token balances.
+//!
+//! This module provides [`BalanceRefresher`], which re-reads the token
+//! balances of a batch of wallets that are older than a configured age.
+
+use std::sync::Arc;
+
+use alloy::primitives::{Address, U256};
+use chrono::Duration;
+use evm_provider::ChainProvider;
+use tracing::{debug, warn};
+
+use super::{TrackedBalance, WalletState};
+use crate::clock::{SharedClock, system_clock};
+use crate::error::Result;
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// REFRESH REPORT
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Outcome of a [`BalanceRefresher::refresh`] run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RefreshReport {
+    /// Balances that were re-read and updated.
+    pub refreshed: usize,
+
+    /// Balances that could not be read and are still stale.
+    pub failed: usize,
+
+    /// Batched multicall reads issued.
+    pub batches: usize,
+
+    /// Block the balances were read at, if any reads were needed.
+    pub block: Option<u64>,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// BALANCE REFRESHER
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Re-reads stale token balances for a batch of wallets.
+///
+/// For every wallet and tracked token whose balance is older than
+/// [`max_age`](Self::with_max_age) (or was never read), the refresher reads
+/// `balanceOf` from the chain and stamps the result with the current block.
+/// When the provider [supports multicall](ChainProvider::supports_multicall)
+/// reads are grouped into batches of [`batch_size`](Self::with_batch_size);
+/// a batch that fails as a whole is retried as single reads. Individual read
+/// failures leave the old balance in place, still stale, so callers relying on
+/// [`WalletState::is_balance_stale`] will not act on it.
+///
+/// # Example
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// use alloy::primitives::{Address, U256};
+/// use evm_provider::mock::MockProvider;
+/// use fleet_core::wallet::{BalanceRefresher, WalletState};
+///
+/// # #[tokio::main]
+/// # async fn main() -> fleet_core::Result<()> {
+/// let token = Address::repeat_byte(0xAA);
+/// let provider = Arc::new(MockProvider::new());
+/// provider.set_token_balance(token, Address::ZERO, U256::from(1000));
+///
+/// let refresher = BalanceRefresher::new(provider, vec![token]);
+/// let mut wallets = vec![WalletState::new("wallet_1".into(), Address::ZERO)];
+///
+/// let report = refresher.refresh(&mut wallets).await?;
+/// assert_eq!(report.refreshed, 1);
+/// assert_eq!(wallets[0].token_balance(token), U256::from(1000));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct BalanceRefresher<P: ChainProvider> {
+    /// Chain provider to read balances from.
+    provider: Arc<P>,
+
+    /// Tokens to keep fresh.
+    tokens: Vec<Address>,
+
+    /// Age after which a balance is re-read.
+    max_age: Duration,
+
+    /// Maximum reads per multicall batch.
+    batch_size: usize,
+
+    /// Source of the current time, for staleness and read timestamps.
+    clock: SharedClock,
+}
+
+impl<P: ChainProvider> BalanceRefresher<P> {
+    /// Default age after which a balance is re-read.
+    pub const DEFAULT_MAX_AGE: Duration = Duration::seconds(60);
+
+    /// Default maximum reads per multicall batch.
+    pub const DEFAULT_BATCH_SIZE: usize = 100;
+
+    /// Create a refresher for the given tokens with default settings.
+    #[must_use]
+    pub fn new(provider: Arc<P>, tokens: Vec<Address>) -> Self {
+        Self {
+            provider,
+            tokens,
+            max_age: Self::DEFAULT_MAX_AGE,
+            batch_size: Self::DEFAULT_BATCH_SIZE,
+            clock: system_clock(),
+        }
+    }
+
+    /// Set the age after which a balance is re-read.
+    ///
+    /// A zero age re-reads every balance.
+    #[must_use]
+    pub const fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = max_age;
+        self
+    }
+
+    /// Set the maximum number of reads per multicall batch (at least 1).
+    #[must_use]
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Use `clock` instead of the system clock.
+    #[must_use]
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Tokens kept fresh by this refresher.
+    #[must_use]
+    pub fn tokens(&self) -> &[Address] {
+        &self.tokens
+    }
+
+    /// Re-read every stale balance of the given wallets.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the current block number cannot be read. Failed
+    /// balance reads are counted in [`RefreshReport::failed`] instead.
+    pub async fn refresh(&self, wallets: &mut [WalletState]) -> Result<RefreshReport> {
+        // (wallet index, token) pairs to read
+        let now = self.clock.now();
+        let stale: Vec<(usize, Address)> = wallets
+            .iter()
+            .enumerate()
+            .flat_map(|(index, wallet)| {
+                self.tokens
+                    .iter()
+                    .filter(|token| wallet.is_balance_stale_at(**token, self.max_age, now))
+                    .map(move |token| (index, *token))
+            })
+            .collect();
+
+        let mut report = RefreshReport::default();
+        if stale.is_empty() {
+            return Ok(report);
+        }
+
+        // Read the block first: the balances are at least this fresh
+        let block = self.provider.get_block_number().await?;
+        report.block = Some(block);
+
+        for chunk in stale.chunks(self.chunk_size()) {
+            let queries: Vec<_> = chunk
+                .iter()
+                .map(|&(index, token)| (token, wallets[index].address))
+                .collect();
+
+            let balances = self.read_chunk(&queries, &mut report).await;
+
+            for (&(index, token), balance) in chunk.iter().zip(balances) {
+                let wallet = &mut wallets[index];
+                if let Some(value) = balance {
+                    wallet.set_tracked_balance(token, TrackedBalance::at(value, block, now));
+                    report.refreshed += 1;
+                } else {
+                    warn!(
+                        wallet = %wallet.id,
+                        token = %token,
+                        "Failed to refresh token balance"
+                    );
+                    report.failed += 1;
+                }
+            }
+        }
+
+        debug!(
+            refreshed = report.refreshed,
+            failed = report.failed,
+            batches = report.batches,
+            block,
+            "Token balances refreshed"
+        );
+
+        Ok(report)
+    }
+
+    /// Number of reads handled per iteration.
+    fn chunk_size(&self) -> usize {
+        if self.provider.supports_multicall() {
+            self.batch_size
+        } else {
+            usize::MAX
+        }
+    }
+
+    /// Read one chunk, batched if possible, one entry per query.
+    async fn read_chunk(
+        &self,
+        queries: &[(Address, Address)],
+        report: &mut RefreshReport,
+    ) -> Vec<Option<U256>> {
+        if self.provider.supports_multicall() {
+            match self.provider.get_token_balances(queries).await {
+                Ok(balances) if balances.len() == queries.len() => {
+                    report.batches += 1;
+                    return balances;
+                }
+                Ok(balances) => warn!(
+                    expected = queries.len(),
+                    got = balances.len(),
+                    "Batched balance read returned wrong number of results, retrying singly"
+                ),
+                Err(e) => warn!(error = %e, "Batched balance read failed, retrying singly"),
+            }
+        }
+
+        let mut balances = Vec::with_capacity(queries.len());
+        for &(token, account) in queries {
+            balances.push(self.provider.get_token_balance(token, account).await.ok());
+        }
+        balances
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+#[allow(clippy::expect_used)]
+mod tests {
+    use chrono::Utc;
+    use evm_provider::mock::MockProvider;
+
+    use super::*;
+
+    const TOKEN: Address = Address::repeat_byte(0xAA);
+
+    fn wallets(count: u8) -> Vec<WalletState> {
+        (1..=count)
+            .map(|i| WalletState::new(format!("wallet_{i}"), Address::repeat_byte(i)))
+            .collect()
+    }
+
+    fn provider(wallets: &[WalletState]) -> Arc<MockProvider> {
+        let provider = Arc::new(MockProvider::new());
+        for (i, wallet) in wallets.iter().enumerate() {
+            provider.set_token_balance(TOKEN, wallet.address, U256::from(100 * (i + 1)));
+        }
+        provider
+    }
+
+    #[tokio::test]
+    async fn refreshes_in_batches_with_multicall() {
+        let mut wallets = wallets(5);
+        let provider = provider(&wallets);
+        provider.set_multicall_support(true);
+        let refresher =
+            BalanceRefresher::new(Arc::clone(&provider), vec![TOKEN]).with_batch_size(2);
+
+        let report = refresher
+            .refresh(&mut wallets)
+            .await
+            .expect("refresh should work");
+
+        assert_eq!(report.refreshed, 5);
+        assert_eq!(report.batches, 3);
+        assert_eq!(provider.token_balance_batches(), [2, 2, 1]);
+        assert_eq!(provider.token_balance_reads(), 0);
+        assert_eq!(wallets[4].token_balance(TOKEN), U256::from(500));
+        let tracked = wallets[0]
+            .tracked_balance(TOKEN)
+            .expect("should be tracked");
+        assert_eq!(Some(tracked.block), report.block);
+    }
+
+    #[tokio::test]
+    async fn reads_singly_without_multicall() {
+        let mut wallets = wallets(3);
+        let provider = provider(&wallets);
+        let refresher =
+            BalanceRefresher::new(Arc::clone(&provider), vec![TOKEN]).with_batch_size(2);
+
+        let report = refresher
+            .refresh(&mut wallets)
+            .await
+            .expect("refresh should work");
+
+        assert_eq!(report.refreshed, 3);
+        assert_eq!(report.batches, 0);
+        assert_eq!(provider.token_balance_reads(), 3);
+    }
+
+    #[tokio::test]
+    async fn skips_fresh_balances() {
+        let mut wallets = wallets(2);
+        let provider = provider(&wallets);
+        wallets[0].set_token_balance(TOKEN, U256::from(1), 1);
+        wallets[1].set_tracked_balance(
+            TOKEN,
+            TrackedBalance::at(U256::from(1), 1, Utc::now() - Duration::minutes(5)),
+        );
+        let refresher = BalanceRefresher::new(Arc::clone(&provider), vec![TOKEN]);
+
+        let report = refresher
+            .refresh(&mut wallets)
+            .await
+            .expect("refresh should work");
+
+        assert_eq!(report.refreshed, 1);
+        assert_eq!(wallets[0].token_balance(TOKEN), U256::from(1));
+        assert_eq!(wallets[1].token_balance(TOKEN), U256::from(200));
+
+        // Nothing stale left: no reads at all
+        let report = refresher
+            .refresh(&mut wallets)
+            .await
+            .expect("refresh should work");
+        assert_eq!(report, RefreshReport::default());
+        assert_eq!(provider.token_balance_reads(), 1);
+    }
+
+    #[tokio::test]
+    async fn uses_injected_clock() {
+        use crate::clock::{Clock, SharedClock, VirtualClock};
+
+        let mut wallets = wallets(1);
+        let provider = provider(&wallets);
+        let clock = Arc::new(VirtualClock::new(Utc::now() + Duration::days(30)));
+        let refresher = BalanceRefresher::new(Arc::clone(&provider), vec![TOKEN])
+            .with_clock(Arc::clone(&clock) as SharedClock);
+        wallets[0].set_token_balance(TOKEN, U256::from(1), 1);
+
+        // Fresh by the wall clock, but a month old by the virtual one
+        let report = refresher
+            .refresh(&mut wallets)
+            .await
+            .expect("refresh should work");
+
+        assert_eq!(report.refreshed, 1);
+        let tracked = wallets[0]
+            .tracked_balance(TOKEN)
+            .expect("should be tracked");
+        assert_eq!(tracked.updated_at, clock.now());
+    }
+
+    #[tokio::test]
+    async fn failed_reads_stay_stale() {
+        let mut wallets = wallets(2);
+        let provider = provider(&wallets);
+        provider.set_multicall_support(true);
+        provider.fail_token_balance(TOKEN, wallets[1].address);
+        let refresher = BalanceRefresher::new(Arc::clone(&provider), vec![TOKEN]);
+
+        let report = refresher
+            .refresh(&mut wallets)
+            .await
+            .expect("refresh should work");
+
+        assert_eq!((report.refreshed, report.failed), (1, 1));
+        assert!(
+            !wallets[0].is_balance_stale(TOKEN, BalanceRefresher::<MockProvider>::DEFAULT_MAX_AGE)
+        );
+        assert!(
+            wallets[1].is_balance_stale(TOKEN, BalanceRefresher::<MockProvider>::DEFAULT_MAX_AGE)
+        );
+    }
+
+    #[tokio::test]
+    async fn block_read_failure_fails_the_refresh() {
+        use evm_provider::ProviderError;
+        use evm_provider::mock_chain::{Method, MockChainProvider, RecordedCall};
+
+        let mut wallets = wallets(2);
+        let chain = Arc::new(MockChainProvider::new());
+        chain.set_block_number(77);
+        chain.fail_next(Method::BlockNumber, ProviderError::Connection("down".into()));
+        let refresher = BalanceRefresher::new(Arc::clone(&chain), vec![TOKEN]);
+
+        assert!(refresher.refresh(&mut wallets).await.is_err());
+        assert_eq!(chain.calls().len(), 1, "no balances read without a block");
+
+        let report = refresher
+            .refresh(&mut wallets)
+            .await
+            .expect("refresh should work");
+        assert_eq!((report.refreshed, report.block), (2, Some(77)));
+        let accounts: Vec<_> = chain
+            .calls_to(Method::TokenBalance)
+            .into_iter()
+            .filter_map(|call| match call {
+                RecordedCall::TokenBalance { account, .. } => Some(account),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(accounts, [wallets[0].address, wallets[1].address]);
+    }
+}
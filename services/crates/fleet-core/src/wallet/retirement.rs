@@ -0,0 +1,177 @@
+//! Retirement of long-lived wallets.
+//!
+//! A wallet that has been active for long enough carries a history operators
+//! may want to leave behind. Retiring it winds it down instead of just
+//! pausing it: a [`Retirement`] starts with the wallet marked retiring, in
+//! which its plugins only exit (extract, claim) and never enter anything new.
+//! Once the plugins hold nothing open for it, whatever it still holds above
+//! the [dust](RetirementSettings) is swept to the retirement's `sweep_to`
+//! address, if it has one. With only dust left the wallet is retired and no
+//! longer scheduled.
+//!
+//! ```text
+//! active ──retire──> retiring ──nothing open, only dust──> retired
+//! ```
+//!
+//! The retirement lives on the [`WalletState`](super::WalletState) and is
+//! serialized with it, so a restored wallet carries on winding down.
+
+use alloy::primitives::{Address, U256};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// SETTINGS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Balances a retiring wallet may keep and still be retired.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetirementSettings {
+    /// Native balance at or below which the wallet holds only dust, in wei.
+    pub dust_native: U256,
+
+    /// Token balance at or below which the wallet holds only dust, in the
+    /// token's smallest unit.
+    pub dust_token: U256,
+}
+
+impl Default for RetirementSettings {
+    fn default() -> Self {
+        Self {
+            // 0.0001 ETH, a handful of transfers' worth of gas
+            dust_native: U256::from(100_000_000_000_000_u64),
+            // 0.01 of an 18-decimal token
+            dust_token: U256::from(10_000_000_000_000_000_u64),
+        }
+    }
+}
+
+impl RetirementSettings {
+    /// Check whether a native balance is only dust.
+    #[must_use]
+    pub fn is_dust_native(&self, balance: U256) -> bool {
+        balance <= self.dust_native
+    }
+
+    /// Check whether a token balance is only dust.
+    #[must_use]
+    pub fn is_dust_token(&self, balance: U256) -> bool {
+        balance <= self.dust_token
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// RETIREMENT
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Stage of a wallet's retirement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RetirementStage {
+    /// Winding down: only exits, then sweeps.
+    Retiring,
+    /// Done: the wallet is no longer scheduled.
+    Retired,
+}
+
+/// A wallet's retirement, from being marked retiring until it is retired.
+///
+/// # Example
+///
+/// ```
+/// use chrono::Utc;
+/// use fleet_core::wallet::{Retirement, RetirementStage};
+///
+/// let now = Utc::now();
+/// let mut retirement = Retirement::new(None, now);
+/// assert_eq!(retirement.stage(), RetirementStage::Retiring);
+///
+/// assert!(retirement.complete(now));
+/// assert_eq!(retirement.stage(), RetirementStage::Retired);
+/// assert!(!retirement.complete(now));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Retirement {
+    /// When the wallet was marked retiring.
+    pub started_at: DateTime<Utc>,
+
+    /// Address the wallet's remaining balances are swept to, if any.
+    ///
+    /// Without one, the wallet is only retired once its balances were moved
+    /// out some other way.
+    #[serde(default)]
+    pub sweep_to: Option<Address>,
+
+    /// When the wallet was retired, once it is.
+    #[serde(default)]
+    pub retired_at: Option<DateTime<Utc>>,
+}
+
+impl Retirement {
+    /// Start a retirement at `now`.
+    #[must_use]
+    pub const fn new(sweep_to: Option<Address>, now: DateTime<Utc>) -> Self {
+        Self {
+            started_at: now,
+            sweep_to,
+            retired_at: None,
+        }
+    }
+
+    /// The retirement's stage.
+    #[must_use]
+    pub const fn stage(&self) -> RetirementStage {
+        if self.retired_at.is_some() {
+            RetirementStage::Retired
+        } else {
+            RetirementStage::Retiring
+        }
+    }
+
+    /// Retire the wallet at `now`. Returns `false` if it already was.
+    pub const fn complete(&mut self, now: DateTime<Utc>) -> bool {
+        if self.retired_at.is_some() {
+            return false;
+        }
+        self.retired_at = Some(now);
+        true
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dust_is_at_or_below_the_threshold() {
+        let settings = RetirementSettings {
+            dust_native: U256::from(100),
+            dust_token: U256::from(10),
+        };
+        assert!(settings.is_dust_native(U256::ZERO));
+        assert!(settings.is_dust_native(U256::from(100)));
+        assert!(!settings.is_dust_native(U256::from(101)));
+        assert!(settings.is_dust_token(U256::from(10)));
+        assert!(!settings.is_dust_token(U256::from(11)));
+    }
+
+    #[test]
+    fn serialized_retirement_reads_back() {
+        let now = Utc::now();
+        let mut retirement = Retirement::new(Some(Address::repeat_byte(0x11)), now);
+        retirement.complete(now);
+
+        let json = serde_json::to_value(retirement).unwrap_or_default();
+        let read: Option<Retirement> = serde_json::from_value(json).ok();
+        assert_eq!(read, Some(retirement));
+
+        // Written before it had a sweep address or was retired
+        let json = serde_json::json!({ "started_at": now });
+        let read: Option<Retirement> = serde_json::from_value(json).ok();
+        assert_eq!(read.map(|r| r.stage()), Some(RetirementStage::Retiring));
+    }
+}
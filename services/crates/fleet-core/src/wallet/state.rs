@@ -6,9 +6,187 @@
 use std::collections::HashMap;
 
 use alloy::primitives::{Address, U256};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
+use crate::error::{FleetError, Result};
+use crate::plugins::{ActionId, PendingFollowUp};
+use crate::safety::SpendLedger;
+
+use super::{Retirement, RetirementSettings, RetirementStage, WarmupPlan, WarmupSettings};
+
+/// Schema version of plugin state stored without one.
+const UNVERSIONED_SCHEMA: u32 = 1;
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// PLUGIN STATE
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// State a plugin keeps on each wallet.
+///
+/// State is stored as JSON together with the schema version it was written
+/// with. Bump [`SCHEMA_VERSION`](Self::SCHEMA_VERSION) when a change cannot
+/// read older state as is, and convert it in [`migrate`](Self::migrate).
+///
+/// # Example
+///
+/// ```
+/// use fleet_core::wallet::{PluginState, WalletState};
+/// use alloy::primitives::Address;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Debug, Default, Serialize, Deserialize)]
+/// struct Bets {
+///     last_round: u64,
+/// }
+///
+/// impl PluginState for Bets {}
+///
+/// let mut wallet = WalletState::new("wallet_1".to_string(), Address::ZERO);
+/// wallet.update_plugin_state("bets", |bets: &mut Bets| bets.last_round = 7)?;
+///
+/// let bets: Option<Bets> = wallet.get_plugin_state("bets")?;
+/// assert_eq!(bets.map(|b| b.last_round), Some(7));
+/// # Ok::<(), fleet_core::FleetError>(())
+/// ```
+pub trait PluginState: Serialize + DeserializeOwned {
+    /// Version of the stored form of this state.
+    const SCHEMA_VERSION: u32 = UNVERSIONED_SCHEMA;
+
+    /// Read state stored with an older schema version.
+    ///
+    /// Default: deserialize it as the current schema, see
+    /// [`decode_plugin_state`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the old state cannot be converted.
+    fn migrate(
+        _from_version: u32,
+        value: serde_json::Value,
+    ) -> std::result::Result<Self, serde_json::Error> {
+        serde_path_to_error::deserialize(value).map_err(path_error)
+    }
+}
+
+/// Read plugin state written with `schema_version` as `T`.
+///
+/// State of an older schema version is read through
+/// [`PluginState::migrate`]. Errors name the field that could not be read,
+/// e.g. `position.amount: invalid type: integer, expected a hex string`.
+///
+/// # Errors
+///
+/// Returns [`FleetError::PluginState`] if the state cannot be read as `T`
+/// or was written with a newer schema version.
+pub fn decode_plugin_state<T: PluginState>(
+    plugin_id: &str,
+    schema_version: u32,
+    value: serde_json::Value,
+) -> Result<T> {
+    check_schema::<T>(plugin_id, schema_version, T::SCHEMA_VERSION)?;
+    let state = if schema_version < T::SCHEMA_VERSION {
+        T::migrate(schema_version, value)
+    } else {
+        serde_path_to_error::deserialize(value).map_err(path_error)
+    };
+    state.map_err(|e| plugin_state_error::<T>(plugin_id, e.to_string()))
+}
+
+/// Prefix a deserialization error with the path of the field it is about.
+fn path_error(error: serde_path_to_error::Error<serde_json::Error>) -> serde_json::Error {
+    let path = error.path().to_string();
+    let inner = error.into_inner();
+    if path == "." {
+        inner
+    } else {
+        serde::de::Error::custom(format!("{path}: {inner}"))
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TRACKED BALANCE
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// A token balance together with when it was read.
+///
+/// Balances change underneath the fleet (incoming transfers, rewards, other
+/// wallets' actions), so decisions should only be made on balances younger
+/// than some threshold. See [`WalletState::is_balance_stale`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "TrackedBalanceRepr")]
+pub struct TrackedBalance {
+    /// Balance in the token's smallest unit.
+    pub value: U256,
+
+    /// Block number the balance was read at.
+    pub block: u64,
+
+    /// When the balance was read.
+    pub updated_at: DateTime<Utc>,
+}
+
+impl TrackedBalance {
+    /// Create a balance read at `block` just now.
+    #[must_use]
+    pub fn new(value: U256, block: u64) -> Self {
+        Self::at(value, block, Utc::now())
+    }
+
+    /// Create a balance read at `block` at the given time.
+    #[must_use]
+    pub const fn at(value: U256, block: u64, updated_at: DateTime<Utc>) -> Self {
+        Self {
+            value,
+            block,
+            updated_at,
+        }
+    }
+
+    /// Age of the balance at `now`.
+    ///
+    /// Zero if `updated_at` lies in the future (clock skew).
+    #[must_use]
+    pub fn age_at(&self, now: DateTime<Utc>) -> Duration {
+        (now - self.updated_at).max(Duration::zero())
+    }
+
+    /// Check whether the balance is older than `max_age` at `now`.
+    #[must_use]
+    pub fn is_stale_at(&self, now: DateTime<Utc>, max_age: Duration) -> bool {
+        self.age_at(now) > max_age
+    }
+}
+
+/// Serialized forms of [`TrackedBalance`].
+///
+/// States persisted before balances were tracked stored the bare value; those
+/// load as read at block 0 at the Unix epoch so they are always stale.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum TrackedBalanceRepr {
+    Tracked {
+        value: U256,
+        block: u64,
+        updated_at: DateTime<Utc>,
+    },
+    Legacy(U256),
+}
+
+impl From<TrackedBalanceRepr> for TrackedBalance {
+    fn from(repr: TrackedBalanceRepr) -> Self {
+        match repr {
+            TrackedBalanceRepr::Tracked {
+                value,
+                block,
+                updated_at,
+            } => Self::at(value, block, updated_at),
+            TrackedBalanceRepr::Legacy(value) => Self::at(value, 0, DateTime::UNIX_EPOCH),
+        }
+    }
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // WALLET STATE
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -27,6 +205,8 @@ use serde::{Deserialize, Serialize};
 /// Plugins can store arbitrary JSON data in [`plugin_states`](Self::plugin_states).
 /// This allows plugins to track protocol-specific information (e.g., current
 /// positions, pending rewards) without modifying the core wallet state structure.
+/// Typed access goes through [`get_plugin_state`](Self::get_plugin_state) and
+/// friends, see [`PluginState`].
 ///
 /// # Example
 ///
@@ -52,8 +232,9 @@ pub struct WalletState {
     /// Token balances by token address.
     ///
     /// Keys are token contract addresses, values are balances in the token's
-    /// smallest unit (e.g., wei for 18-decimal tokens).
-    pub token_balances: HashMap<Address, U256>,
+    /// smallest unit (e.g., wei for 18-decimal tokens) along with the block
+    /// and time they were read at.
+    pub token_balances: HashMap<Address, TrackedBalance>,
 
     /// Current confirmed nonce (transaction count).
     ///
@@ -67,9 +248,22 @@ pub struct WalletState {
     /// Plugins are responsible for serializing/deserializing their own state.
     pub plugin_states: HashMap<String, serde_json::Value>,
 
+    /// Schema version of each entry in `plugin_states`.
+    ///
+    /// Entries without one were stored before versioning and count as
+    /// version 1.
+    #[serde(default)]
+    pub plugin_state_schema_versions: HashMap<String, u32>,
+
     /// Timestamp of last successful action.
     pub last_action: Option<DateTime<Utc>>,
 
+    /// When each action was last executed, for cooldowns.
+    ///
+    /// See [`ActionCooldowns`](crate::plugins::ActionCooldowns).
+    #[serde(default)]
+    pub last_executed: HashMap<ActionId, DateTime<Utc>>,
+
     /// When the next action should be considered.
     ///
     /// The scheduler uses this to determine when to check this wallet again.
@@ -96,6 +290,36 @@ pub struct WalletState {
     ///
     /// References a profile in the configuration (e.g., "whale", "degen").
     pub profile_name: String,
+
+    /// Group of related wallets (e.g., funded from the same source).
+    ///
+    /// See [`GroupLimiter`](crate::scheduler::GroupLimiter).
+    #[serde(default)]
+    pub group: Option<String>,
+
+    /// What the wallet spent today and overall, against its budget.
+    ///
+    /// See [`BudgetManager`](crate::safety::BudgetManager).
+    #[serde(default)]
+    pub budget: SpendLedger,
+
+    /// The wallet's warm-up plan, until it completes.
+    ///
+    /// See [`WarmupPlan`].
+    #[serde(default)]
+    pub warmup: Option<WarmupPlan>,
+
+    /// The wallet's retirement, once it was marked retiring.
+    ///
+    /// See [`Retirement`].
+    #[serde(default)]
+    pub retirement: Option<Retirement>,
+
+    /// Follow-ups of the wallet's past actions, waiting to run.
+    ///
+    /// See [`follow_up`](crate::plugins::follow_up).
+    #[serde(default)]
+    pub follow_ups: Vec<PendingFollowUp>,
 }
 
 impl WalletState {
@@ -113,12 +337,19 @@ impl WalletState {
             token_balances: HashMap::new(),
             nonce: 0,
             plugin_states: HashMap::new(),
+            plugin_state_schema_versions: HashMap::new(),
             last_action: None,
+            last_executed: HashMap::new(),
             next_action: Utc::now(),
             active: true,
             consecutive_errors: 0,
             afk_until: None,
             profile_name: String::new(),
+            group: None,
+            budget: SpendLedger::default(),
+            warmup: None,
+            retirement: None,
+            follow_ups: Vec::new(),
         }
     }
 
@@ -135,8 +366,13 @@ impl WalletState {
     /// Returns `true` if `afk_until` is set and is in the future.
     #[must_use]
     pub fn is_afk(&self) -> bool {
-        self.afk_until
-            .is_some_and(|until| Utc::now() < until)
+        self.is_afk_at(Utc::now())
+    }
+
+    /// Check if the wallet is AFK at `now`.
+    #[must_use]
+    pub fn is_afk_at(&self, now: DateTime<Utc>) -> bool {
+        self.afk_until.is_some_and(|until| now < until)
     }
 
     /// Check if the wallet is active and ready to act.
@@ -144,7 +380,13 @@ impl WalletState {
     /// Returns `true` if the wallet is active and not AFK.
     #[must_use]
     pub fn is_active(&self) -> bool {
-        self.active && !self.is_afk()
+        self.is_active_at(Utc::now())
+    }
+
+    /// Check if the wallet is active, not retired and not AFK at `now`.
+    #[must_use]
+    pub fn is_active_at(&self, now: DateTime<Utc>) -> bool {
+        self.active && !self.is_retired() && !self.is_afk_at(now)
     }
 
     /// Check if it's time for this wallet to consider an action.
@@ -152,7 +394,13 @@ impl WalletState {
     /// Returns `true` if the current time is at or past `next_action`.
     #[must_use]
     pub fn is_due(&self) -> bool {
-        Utc::now() >= self.next_action
+        self.is_due_at(Utc::now())
+    }
+
+    /// Check if the wallet should consider an action at `now`.
+    #[must_use]
+    pub fn is_due_at(&self, now: DateTime<Utc>) -> bool {
+        now >= self.next_action
     }
 
     /// Get the balance of a specific token.
@@ -160,7 +408,65 @@ impl WalletState {
     /// Returns `U256::ZERO` if the token is not tracked.
     #[must_use]
     pub fn token_balance(&self, token: Address) -> U256 {
-        self.token_balances.get(&token).copied().unwrap_or(U256::ZERO)
+        self.token_balances
+            .get(&token)
+            .map_or(U256::ZERO, |balance| balance.value)
+    }
+
+    /// Get the balance of a specific token with its freshness information.
+    ///
+    /// Returns `None` if the token is not tracked.
+    #[must_use]
+    pub fn tracked_balance(&self, token: Address) -> Option<&TrackedBalance> {
+        self.token_balances.get(&token)
+    }
+
+    /// Check whether the balance of a token is older than `max_age`.
+    ///
+    /// Untracked tokens count as stale.
+    #[must_use]
+    pub fn is_balance_stale(&self, token: Address, max_age: Duration) -> bool {
+        self.is_balance_stale_at(token, max_age, Utc::now())
+    }
+
+    /// Check whether the balance of a token is older than `max_age` at `now`.
+    #[must_use]
+    pub fn is_balance_stale_at(
+        &self,
+        token: Address,
+        max_age: Duration,
+        now: DateTime<Utc>,
+    ) -> bool {
+        self.token_balances
+            .get(&token)
+            .is_none_or(|balance| balance.is_stale_at(now, max_age))
+    }
+
+    /// Tracked tokens whose balances are older than `max_age`.
+    ///
+    /// Tokens that were never read are not included since the wallet does not
+    /// know about them; pass them to the [`BalanceRefresher`](super::BalanceRefresher)
+    /// explicitly.
+    #[must_use]
+    pub fn balances_needing_refresh(&self, max_age: Duration) -> Vec<Address> {
+        self.balances_needing_refresh_at(max_age, Utc::now())
+    }
+
+    /// Tracked tokens whose balances are older than `max_age` at `now`.
+    #[must_use]
+    pub fn balances_needing_refresh_at(
+        &self,
+        max_age: Duration,
+        now: DateTime<Utc>,
+    ) -> Vec<Address> {
+        let mut tokens: Vec<_> = self
+            .token_balances
+            .iter()
+            .filter(|(_, balance)| balance.is_stale_at(now, max_age))
+            .map(|(token, _)| *token)
+            .collect();
+        tokens.sort_unstable();
+        tokens
     }
 
     /// Get plugin-specific state.
@@ -171,41 +477,252 @@ impl WalletState {
         self.plugin_states.get(plugin_id)
     }
 
-    /// Get plugin-specific state, deserialized to a specific type.
+    /// Get the schema version plugin-specific state was stored with.
+    ///
+    /// Returns `None` if no state exists for the given plugin.
+    #[must_use]
+    pub fn plugin_state_schema_version(&self, plugin_id: &str) -> Option<u32> {
+        self.plugin_states.contains_key(plugin_id).then(|| {
+            self.plugin_state_schema_versions
+                .get(plugin_id)
+                .copied()
+                .unwrap_or(UNVERSIONED_SCHEMA)
+        })
+    }
+
+    /// Get plugin-specific state as `T`.
+    ///
+    /// State stored with an older schema version is read through
+    /// [`PluginState::migrate`]. Returns `Ok(None)` if no state exists.
     ///
-    /// Returns `None` if no state exists or deserialization fails.
-    pub fn plugin_state_as<T: serde::de::DeserializeOwned>(&self, plugin_id: &str) -> Option<T> {
-        self.plugin_states
-            .get(plugin_id)
-            .and_then(|v| serde_json::from_value(v.clone()).ok())
+    /// # Errors
+    ///
+    /// Returns [`FleetError::PluginState`] if the state cannot be read as
+    /// `T` or was stored with a newer schema version.
+    pub fn get_plugin_state<T: PluginState>(&self, plugin_id: &str) -> Result<Option<T>> {
+        let (Some(value), Some(version)) = (
+            self.plugin_states.get(plugin_id),
+            self.plugin_state_schema_version(plugin_id),
+        ) else {
+            return Ok(None);
+        };
+        decode_plugin_state(plugin_id, version, value.clone()).map(Some)
     }
 
     /// Set plugin-specific state.
-    pub fn set_plugin_state(&mut self, plugin_id: &str, state: serde_json::Value) {
-        self.plugin_states.insert(plugin_id.to_string(), state);
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FleetError::PluginState`] if serialization fails or the
+    /// stored state has a newer schema version, which is then kept.
+    pub fn set_plugin_state<T: PluginState>(&mut self, plugin_id: &str, state: &T) -> Result<()> {
+        let value = serde_json::to_value(state)
+            .map_err(|e| plugin_state_error::<T>(plugin_id, e.to_string()))?;
+        if let Some(version) = self.plugin_state_schema_version(plugin_id) {
+            check_schema::<T>(plugin_id, version, T::SCHEMA_VERSION)?;
+        }
+        self.insert_plugin_state(plugin_id, value, T::SCHEMA_VERSION);
+        Ok(())
+    }
+
+    /// Read, modify and write back plugin-specific state.
+    ///
+    /// Starts from `T::default()` if no state exists. The state is only
+    /// written back once `update` returns.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FleetError::PluginState`] if the stored state cannot be
+    /// read as `T`, leaving it untouched.
+    pub fn update_plugin_state<T, R>(
+        &mut self,
+        plugin_id: &str,
+        update: impl FnOnce(&mut T) -> R,
+    ) -> Result<R>
+    where
+        T: PluginState + Default,
+    {
+        let mut state = self.get_plugin_state::<T>(plugin_id)?.unwrap_or_default();
+        let result = update(&mut state);
+        self.set_plugin_state(plugin_id, &state)?;
+        Ok(result)
     }
 
-    /// Set plugin-specific state from a serializable value.
+    /// Set plugin-specific state as JSON written with `schema_version`.
     ///
     /// # Errors
     ///
-    /// Returns an error if serialization fails.
-    pub fn set_plugin_state_from<T: Serialize>(
+    /// Returns [`FleetError::PluginState`] if the stored state has a newer
+    /// schema version, which is then kept.
+    pub fn set_raw_plugin_state(
         &mut self,
         plugin_id: &str,
-        state: &T,
-    ) -> Result<(), serde_json::Error> {
-        let value = serde_json::to_value(state)?;
-        self.set_plugin_state(plugin_id, value);
+        state: serde_json::Value,
+        schema_version: u32,
+    ) -> Result<()> {
+        if let Some(version) = self.plugin_state_schema_version(plugin_id) {
+            check_schema::<serde_json::Value>(plugin_id, version, schema_version)?;
+        }
+        self.insert_plugin_state(plugin_id, state, schema_version);
         Ok(())
     }
 
+    fn insert_plugin_state(&mut self, plugin_id: &str, state: serde_json::Value, version: u32) {
+        self.plugin_states.insert(plugin_id.to_string(), state);
+        self.plugin_state_schema_versions
+            .insert(plugin_id.to_string(), version);
+    }
+
     /// Record a successful action.
     ///
     /// Resets error count and updates last action timestamp.
     pub fn record_success(&mut self) {
+        self.record_success_at(Utc::now());
+    }
+
+    /// Record a successful action at the given time.
+    pub const fn record_success_at(&mut self, at: DateTime<Utc>) {
         self.consecutive_errors = 0;
-        self.last_action = Some(Utc::now());
+        self.last_action = Some(at);
+    }
+
+    /// Record that an action was executed at the given time.
+    pub fn record_execution(&mut self, action: ActionId, at: DateTime<Utc>) {
+        self.last_executed.insert(action, at);
+    }
+
+    /// Start warming the wallet up, if it is new.
+    ///
+    /// Wallets that already acted, or already have a plan (e.g. restored
+    /// mid warm-up), keep what they have. Returns whether a plan was started.
+    pub fn start_warmup(
+        &mut self,
+        settings: &WarmupSettings,
+        seed: u64,
+        now: DateTime<Utc>,
+    ) -> bool {
+        let start = settings.enabled && self.warmup.is_none() && self.last_action.is_none();
+        if start {
+            self.warmup = Some(WarmupPlan::generate(settings, seed, now));
+        }
+        start
+    }
+
+    /// The wallet's warm-up plan, if it is still warming up at `now`.
+    #[must_use]
+    pub fn warmup_at(&self, now: DateTime<Utc>) -> Option<&WarmupPlan> {
+        self.warmup
+            .as_ref()
+            .filter(|plan| !plan.is_complete_at(now))
+    }
+
+    /// Record that the wallet took its due warm-up step, if any.
+    pub fn record_warmup_step(&mut self, at: DateTime<Utc>) {
+        if let Some(plan) = &mut self.warmup {
+            plan.complete_due_step(at);
+        }
+    }
+
+    /// Bring the warm-up plan up to date at `now`.
+    ///
+    /// Stretches a stalled plan and drops a completed one, after which the
+    /// wallet behaves normally. Returns whether the plan completed.
+    pub fn advance_warmup(&mut self, now: DateTime<Utc>) -> bool {
+        let Some(plan) = &mut self.warmup else {
+            return false;
+        };
+        plan.stretch_at(now);
+        let complete = plan.is_complete_at(now);
+        if complete {
+            self.warmup = None;
+        }
+        complete
+    }
+
+    /// Mark the wallet retiring at `now`, see [`Retirement`].
+    ///
+    /// A wallet that is already retiring keeps its retirement, taking
+    /// `sweep_to` if one is given. Returns whether the wallet started
+    /// retiring; a retired wallet never does.
+    pub fn start_retiring(&mut self, sweep_to: Option<Address>, now: DateTime<Utc>) -> bool {
+        let Some(retirement) = &mut self.retirement else {
+            self.retirement = Some(Retirement::new(sweep_to, now));
+            return true;
+        };
+        if retirement.stage() == RetirementStage::Retiring && sweep_to.is_some() {
+            retirement.sweep_to = sweep_to;
+        }
+        false
+    }
+
+    /// Stage of the wallet's retirement, `None` if it is not retiring.
+    #[must_use]
+    pub fn retirement_stage(&self) -> Option<RetirementStage> {
+        self.retirement.as_ref().map(Retirement::stage)
+    }
+
+    /// Check if the wallet is winding down, see [`Retirement`].
+    #[must_use]
+    pub fn is_retiring(&self) -> bool {
+        self.retirement_stage() == Some(RetirementStage::Retiring)
+    }
+
+    /// Check if the wallet is retired.
+    #[must_use]
+    pub fn is_retired(&self) -> bool {
+        self.retirement_stage() == Some(RetirementStage::Retired)
+    }
+
+    /// Check if the wallet holds no more than dust of its native balance and
+    /// of each of `tokens`.
+    #[must_use]
+    pub fn holds_only_dust(&self, settings: &RetirementSettings, tokens: &[Address]) -> bool {
+        settings.is_dust_native(self.native_balance)
+            && tokens
+                .iter()
+                .all(|token| settings.is_dust_token(self.token_balance(*token)))
+    }
+
+    /// Retire a retiring wallet at `now`, which takes it out of scheduling
+    /// for good.
+    ///
+    /// Returns `false`, leaving the wallet as it is, if it is not retiring.
+    pub fn retire(&mut self, now: DateTime<Utc>) -> bool {
+        if !self.is_retiring() {
+            return false;
+        }
+        let retired = self
+            .retirement
+            .as_mut()
+            .is_some_and(|retirement| retirement.complete(now));
+        self.active = false;
+        self.warmup = None;
+        self.follow_ups.clear();
+        retired
+    }
+
+    /// Queue a follow-up, bringing the next action forward to when it is due.
+    ///
+    /// Follow-ups run in the order they become due, those due at the same
+    /// time in the order they were queued.
+    pub fn queue_follow_up(&mut self, pending: PendingFollowUp) {
+        self.next_action = self.next_action.min(pending.due);
+        let at = self.follow_ups.partition_point(|queued| queued.due <= pending.due);
+        self.follow_ups.insert(at, pending);
+    }
+
+    /// When the first queued follow-up is due, if any is queued.
+    #[must_use]
+    pub fn next_follow_up_due(&self) -> Option<DateTime<Utc>> {
+        self.follow_ups.first().map(|pending| pending.due)
+    }
+
+    /// Take the first follow-up that is due at `now`.
+    pub fn take_due_follow_up(&mut self, now: DateTime<Utc>) -> Option<PendingFollowUp> {
+        self.follow_ups
+            .first()
+            .is_some_and(|pending| pending.is_due_at(now))
+            .then(|| self.follow_ups.remove(0))
     }
 
     /// Record a failed action.
@@ -235,8 +752,13 @@ impl WalletState {
         self.native_balance = balance;
     }
 
-    /// Update token balance.
-    pub fn set_token_balance(&mut self, token: Address, balance: U256) {
+    /// Update token balance as read at `block` just now.
+    pub fn set_token_balance(&mut self, token: Address, balance: U256, block: u64) {
+        self.set_tracked_balance(token, TrackedBalance::new(balance, block));
+    }
+
+    /// Update token balance with explicit freshness information.
+    pub fn set_tracked_balance(&mut self, token: Address, balance: TrackedBalance) {
         self.token_balances.insert(token, balance);
     }
 
@@ -253,11 +775,32 @@ impl WalletState {
     }
 }
 
+/// Refuse plugin state stored with a newer schema version than `known`.
+fn check_schema<T>(plugin_id: &str, stored: u32, known: u32) -> Result<()> {
+    if stored > known {
+        return Err(plugin_state_error::<T>(
+            plugin_id,
+            format!("stored with schema version {stored}, newer than {known}"),
+        ));
+    }
+    Ok(())
+}
+
+/// Error for plugin state that cannot be read or written as `T`.
+fn plugin_state_error<T>(plugin_id: &str, message: String) -> FleetError {
+    FleetError::PluginState {
+        plugin: plugin_id.to_string(),
+        type_name: std::any::type_name::<T>(),
+        message,
+    }
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // TESTS
 // ═══════════════════════════════════════════════════════════════════════════════
 
 #[cfg(test)]
+#[allow(clippy::expect_used)]
 mod tests {
     use super::*;
     use chrono::Duration;
@@ -290,6 +833,22 @@ mod tests {
         assert!(!wallet.is_afk());
     }
 
+    #[test]
+    fn timing_at_given_time() {
+        let mut wallet = WalletState::new("test".into(), Address::ZERO);
+        let now = Utc::now();
+        wallet.set_afk(now + Duration::hours(1));
+        wallet.schedule_next(now + Duration::hours(1));
+
+        assert!(wallet.is_afk_at(now));
+        assert!(!wallet.is_active_at(now));
+        assert!(!wallet.is_due_at(now));
+
+        let later = now + Duration::hours(1);
+        assert!(wallet.is_active_at(later));
+        assert!(wallet.is_due_at(later));
+    }
+
     #[test]
     fn error_tracking() {
         let mut wallet = WalletState::new("test".into(), Address::ZERO);
@@ -310,30 +869,368 @@ mod tests {
 
         assert_eq!(wallet.token_balance(token), U256::ZERO);
 
-        wallet.set_token_balance(token, U256::from(1000));
+        wallet.set_token_balance(token, U256::from(1000), 7);
         assert_eq!(wallet.token_balance(token), U256::from(1000));
+        assert_eq!(wallet.tracked_balance(token).map(|b| b.block), Some(7));
+    }
+
+    #[test]
+    fn balance_staleness() {
+        let now = Utc::now();
+        let balance = TrackedBalance::at(U256::from(1), 1, now - Duration::seconds(30));
+
+        assert_eq!(balance.age_at(now), Duration::seconds(30));
+        assert!(!balance.is_stale_at(now, Duration::seconds(30)));
+        assert!(balance.is_stale_at(now, Duration::seconds(29)));
+
+        // Reads from the future are treated as brand new
+        let skewed = TrackedBalance::at(U256::from(1), 1, now + Duration::seconds(5));
+        assert_eq!(skewed.age_at(now), Duration::zero());
+        assert!(!skewed.is_stale_at(now, Duration::zero()));
+    }
+
+    #[test]
+    fn balances_needing_refresh() {
+        let mut wallet = WalletState::new("test".into(), Address::ZERO);
+        let (fresh, stale, unknown) = (
+            Address::repeat_byte(0xAA),
+            Address::repeat_byte(0xBB),
+            Address::repeat_byte(0xCC),
+        );
+        let max_age = Duration::minutes(5);
+
+        wallet.set_token_balance(fresh, U256::from(1), 10);
+        wallet.set_tracked_balance(
+            stale,
+            TrackedBalance::at(U256::from(2), 9, Utc::now() - Duration::minutes(10)),
+        );
+
+        assert!(!wallet.is_balance_stale(fresh, max_age));
+        assert!(wallet.is_balance_stale(stale, max_age));
+        assert!(wallet.is_balance_stale(unknown, max_age));
+        assert_eq!(wallet.balances_needing_refresh(max_age), [stale]);
+    }
+
+    #[test]
+    fn tracked_balances_round_trip() {
+        let mut wallet = WalletState::new("test".into(), Address::ZERO);
+        let token = Address::repeat_byte(0xAA);
+        wallet.set_token_balance(token, U256::from(1000), 42);
+
+        let json = serde_json::to_string(&wallet).expect("serialization should work");
+        let restored: WalletState =
+            serde_json::from_str(&json).expect("deserialization should work");
+
+        assert_eq!(restored.token_balances, wallet.token_balances);
+    }
+
+    #[test]
+    fn last_executed_round_trip() {
+        let mut wallet = WalletState::new("test".into(), Address::ZERO);
+        wallet.record_execution("ghostnet.hashcrash_bet".into(), Utc::now());
+
+        let json = serde_json::to_value(&wallet).expect("serialization should work");
+        assert!(json["last_executed"]["ghostnet.hashcrash_bet"].is_string());
+        let restored: WalletState =
+            serde_json::from_value(json).expect("deserialization should work");
+        assert_eq!(restored.last_executed, wallet.last_executed);
+
+        // Snapshots from before cooldowns load with none recorded
+        let mut json = serde_json::to_value(&wallet).expect("serialization should work");
+        json.as_object_mut()
+            .expect("should be an object")
+            .remove("last_executed");
+        let restored: WalletState =
+            serde_json::from_value(json).expect("deserialization should work");
+        assert!(restored.last_executed.is_empty());
+    }
+
+    #[test]
+    fn warmup_resumes_after_restore() {
+        let settings = WarmupSettings {
+            enabled: true,
+            ..WarmupSettings::default()
+        };
+        let now = Utc::now();
+        let mut wallet = WalletState::new("test".into(), Address::ZERO);
+        assert!(wallet.start_warmup(&settings, 9, now));
+        let first = wallet.warmup.as_ref().expect("should have a plan").steps[0].due_at;
+        wallet.record_warmup_step(first);
+        wallet.record_success_at(first);
+
+        // A restart keeps the plan and its progress instead of starting over
+        let json = serde_json::to_value(&wallet).expect("serialization should work");
+        let mut restored: WalletState =
+            serde_json::from_value(json).expect("deserialization should work");
+        assert!(!restored.start_warmup(&settings, 10, first));
+        assert_eq!(restored.warmup, wallet.warmup);
+        assert_eq!(
+            restored.warmup_at(first).map(|plan| plan.completed),
+            Some(1)
+        );
+
+        // Finishing the plan flips the wallet back to normal behavior
+        let plan = restored.warmup.clone().expect("should have a plan");
+        for step in &plan.steps[1..] {
+            restored.record_warmup_step(step.due_at);
+        }
+        assert!(!restored.advance_warmup(plan.ends_at - Duration::hours(1)));
+        assert!(restored.advance_warmup(plan.ends_at));
+        assert!(restored.warmup_at(plan.ends_at).is_none());
+
+        // Wallets that already acted never warm up
+        assert!(!restored.start_warmup(&settings, 9, plan.ends_at));
+    }
+
+    #[test]
+    fn follow_ups_run_in_due_order_after_restore() {
+        use crate::plugins::{FollowUpAction, PendingFollowUp};
+
+        let now = Utc::now();
+        let pending = |action: &str, due| PendingFollowUp {
+            plugin_id: "test_plugin".into(),
+            follow_up: FollowUpAction::new(action, action),
+            due,
+            depth: 1,
+        };
+        let mut wallet = WalletState::new("test".into(), Address::ZERO);
+        wallet.schedule_next(now + Duration::hours(2));
+        wallet.queue_follow_up(pending("late", now + Duration::minutes(10)));
+        wallet.queue_follow_up(pending("early", now + Duration::minutes(5)));
+        wallet.queue_follow_up(pending("early_too", now + Duration::minutes(5)));
+
+        // The next action moved forward to the first follow-up
+        assert_eq!(wallet.next_action, now + Duration::minutes(5));
+        assert_eq!(wallet.next_follow_up_due(), Some(now + Duration::minutes(5)));
+
+        let json = serde_json::to_value(&wallet).expect("serialization should work");
+        let mut restored: WalletState =
+            serde_json::from_value(json).expect("deserialization should work");
+        assert!(restored.take_due_follow_up(now).is_none());
+        let order: Vec<_> = std::iter::from_fn(|| {
+            restored.take_due_follow_up(now + Duration::minutes(10))
+        })
+        .map(|pending| pending.follow_up.action.to_string())
+        .collect();
+        assert_eq!(order, ["early", "early_too", "late"]);
+
+        // Snapshots from before follow-ups load with none queued
+        let mut json = serde_json::to_value(&wallet).expect("serialization should work");
+        json.as_object_mut()
+            .expect("should be an object")
+            .remove("follow_ups");
+        let restored: WalletState =
+            serde_json::from_value(json).expect("deserialization should work");
+        assert!(restored.follow_ups.is_empty());
+    }
+
+    #[test]
+    fn retirement_moves_from_retiring_to_retired() {
+        let now = Utc::now();
+        let sweep_to = Address::repeat_byte(0x11);
+        let mut wallet = WalletState::new("test".into(), Address::ZERO);
+        assert_eq!(wallet.retirement_stage(), None);
+        assert!(!wallet.retire(now), "only retiring wallets retire");
+
+        assert!(wallet.start_retiring(None, now));
+        assert!(wallet.is_retiring() && wallet.is_active_at(now));
+        // Marking it again keeps the retirement, but takes a sweep address
+        assert!(!wallet.start_retiring(Some(sweep_to), now + Duration::hours(1)));
+        let retirement = wallet.retirement.expect("should be retiring");
+        assert_eq!((retirement.started_at, retirement.sweep_to), (now, Some(sweep_to)));
+
+        assert!(wallet.retire(now + Duration::hours(2)));
+        assert!(wallet.is_retired() && !wallet.is_retiring());
+        assert!(!wallet.is_active_at(now + Duration::hours(2)));
+        // Retired is final
+        assert!(!wallet.retire(now + Duration::hours(3)));
+        assert!(!wallet.start_retiring(None, now + Duration::hours(3)));
+        wallet.active = true;
+        assert!(!wallet.is_active_at(now + Duration::hours(3)));
+
+        // A restart keeps the wallet retired
+        let json = serde_json::to_value(&wallet).expect("serialization should work");
+        let restored: WalletState =
+            serde_json::from_value(json).expect("deserialization should work");
+        assert_eq!(restored.retirement, wallet.retirement);
     }
 
     #[test]
-    fn plugin_state() {
+    fn only_dust_is_checked_per_balance() {
+        let settings = RetirementSettings {
+            dust_native: U256::from(100),
+            dust_token: U256::from(10),
+        };
+        let token = Address::repeat_byte(0xAA);
         let mut wallet = WalletState::new("test".into(), Address::ZERO);
+        wallet.set_native_balance(U256::from(100));
+        wallet.set_token_balance(token, U256::from(10), 1);
+        assert!(wallet.holds_only_dust(&settings, &[token]));
+
+        wallet.set_token_balance(token, U256::from(11), 2);
+        assert!(!wallet.holds_only_dust(&settings, &[token]));
+        // Untracked tokens are not checked
+        assert!(wallet.holds_only_dust(&settings, &[]));
+
+        wallet.set_native_balance(U256::from(101));
+        assert!(!wallet.holds_only_dust(&settings, &[]));
+    }
+
+    #[test]
+    fn legacy_balances_load_as_stale() {
+        let token = Address::repeat_byte(0xAA);
+        let mut json = serde_json::to_value(WalletState::new("test".into(), Address::ZERO))
+            .expect("serialization should work");
+        json["token_balances"] = serde_json::json!({ token.to_string(): "0x3e8" });
+
+        let wallet: WalletState =
+            serde_json::from_value(json).expect("deserialization should work");
+
+        assert_eq!(wallet.token_balance(token), U256::from(1000));
+        assert!(wallet.is_balance_stale(token, Duration::days(365)));
+    }
+
+    #[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+    struct TestState {
+        value: u64,
+    }
 
-        #[derive(Debug, Serialize, Deserialize, PartialEq)]
-        struct TestState {
-            value: u64,
+    impl PluginState for TestState {}
+
+    /// `TestState` after `value` was renamed to `count`.
+    #[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+    struct TestStateV2 {
+        count: u64,
+    }
+
+    impl PluginState for TestStateV2 {
+        const SCHEMA_VERSION: u32 = 2;
+
+        fn migrate(
+            _from_version: u32,
+            value: serde_json::Value,
+        ) -> std::result::Result<Self, serde_json::Error> {
+            let old: TestState = serde_json::from_value(value)?;
+            Ok(Self { count: old.value })
         }
+    }
+
+    #[test]
+    fn plugin_state_roundtrips() {
+        let mut wallet = WalletState::new("test".into(), Address::ZERO);
 
         let state = TestState { value: 42 };
         wallet
-            .set_plugin_state_from("test_plugin", &state)
+            .set_plugin_state("test_plugin", &state)
+            .expect("serialization should work");
+
+        let retrieved = wallet.get_plugin_state::<TestState>("test_plugin");
+        assert_eq!(retrieved.expect("should read"), Some(state));
+        assert_eq!(wallet.plugin_state_schema_version("test_plugin"), Some(1));
+
+        let missing = wallet.get_plugin_state::<TestState>("nonexistent");
+        assert!(missing.expect("should read").is_none());
+    }
+
+    #[test]
+    fn plugin_state_of_another_type_is_an_error() {
+        let mut wallet = WalletState::new("test".into(), Address::ZERO);
+        wallet
+            .set_raw_plugin_state("test_plugin", serde_json::json!({ "value": "x" }), 1)
+            .expect("nothing stored yet");
+
+        let err = wallet
+            .get_plugin_state::<TestState>("test_plugin")
+            .expect_err("value is not a number");
+        assert!(matches!(
+            &err,
+            FleetError::PluginState { plugin, type_name, .. }
+                if plugin == "test_plugin" && type_name.ends_with("TestState")
+        ));
+        assert!(err.to_string().contains("value: invalid type"), "{err}");
+
+        // The update is refused and the stored value kept
+        assert!(
+            wallet
+                .update_plugin_state("test_plugin", |s: &mut TestState| s.value += 1)
+                .is_err()
+        );
+        assert_eq!(
+            wallet.plugin_state("test_plugin"),
+            Some(&serde_json::json!({ "value": "x" }))
+        );
+    }
+
+    #[test]
+    fn plugin_state_schema_versions() {
+        let mut wallet = WalletState::new("test".into(), Address::ZERO);
+        wallet
+            .set_plugin_state("test_plugin", &TestState { value: 7 })
+            .expect("serialization should work");
+
+        // A newer version migrates the old state
+        let migrated = wallet.get_plugin_state::<TestStateV2>("test_plugin");
+        assert_eq!(
+            migrated.expect("should migrate"),
+            Some(TestStateV2 { count: 7 })
+        );
+        wallet
+            .set_plugin_state("test_plugin", &TestStateV2 { count: 8 })
+            .expect("newer version may replace older state");
+        assert_eq!(wallet.plugin_state_schema_version("test_plugin"), Some(2));
+
+        // After a rollback, the older version neither reads nor clobbers it
+        assert!(wallet.get_plugin_state::<TestState>("test_plugin").is_err());
+        assert!(
+            wallet
+                .set_plugin_state("test_plugin", &TestState { value: 0 })
+                .is_err()
+        );
+        assert!(
+            wallet
+                .set_raw_plugin_state("test_plugin", serde_json::json!({}), 1)
+                .is_err()
+        );
+        let kept = wallet.get_plugin_state::<TestStateV2>("test_plugin");
+        assert_eq!(kept.expect("should read"), Some(TestStateV2 { count: 8 }));
+    }
+
+    #[test]
+    fn update_plugin_state_starts_from_default() {
+        let mut wallet = WalletState::new("test".into(), Address::ZERO);
+
+        let bump = |state: &mut TestState| {
+            state.value += 1;
+            state.value
+        };
+        assert_eq!(
+            wallet.update_plugin_state("test_plugin", bump).ok(),
+            Some(1)
+        );
+        assert_eq!(
+            wallet.update_plugin_state("test_plugin", bump).ok(),
+            Some(2)
+        );
+
+        let stored = wallet.get_plugin_state::<TestState>("test_plugin");
+        assert_eq!(stored.expect("should read"), Some(TestState { value: 2 }));
+    }
+
+    #[test]
+    fn unversioned_plugin_state_loads_as_version_one() {
+        let mut json = serde_json::to_value(WalletState::new("test".into(), Address::ZERO))
             .expect("serialization should work");
+        json["plugin_states"] = serde_json::json!({ "test_plugin": { "value": 3 } });
+        json.as_object_mut()
+            .expect("wallet is an object")
+            .remove("plugin_state_schema_versions");
 
-        let retrieved: TestState = wallet
-            .plugin_state_as("test_plugin")
-            .expect("should have state");
-        assert_eq!(retrieved, state);
+        let wallet: WalletState =
+            serde_json::from_value(json).expect("deserialization should work");
 
-        assert!(wallet.plugin_state_as::<TestState>("nonexistent").is_none());
+        assert_eq!(wallet.plugin_state_schema_version("test_plugin"), Some(1));
+        let state = wallet.get_plugin_state::<TestState>("test_plugin");
+        assert_eq!(state.expect("should read"), Some(TestState { value: 3 }));
     }
 
     #[test]
@@ -0,0 +1,360 @@
+//! Warm-up plans for new wallets.
+//!
+//! A brand new wallet that stakes and bets like a seasoned one from its first
+//! hour stands out. A [`WarmupPlan`] eases it in instead: over its first days
+//! the wallet takes a short, randomized sequence of small steps (an entry, a
+//! couple of bets, a claim) with idle gaps in between, while its amounts are
+//! scaled by a factor that ramps up to 1.0 by the end of the plan.
+//!
+//! The plan lives on the [`WalletState`](super::WalletState) and is persisted
+//! with it, so a restart resumes a plan where it left off. Plugins map the
+//! steps to their own actions, see [`WarmupStepKind`].
+
+// Allow precision loss for plan durations (seconds, far below 2^52)
+#![allow(clippy::cast_precision_loss)]
+// Allow suboptimal floating point ops - readability over micro-optimization
+#![allow(clippy::suboptimal_flops)]
+
+use chrono::{DateTime, Duration, Utc};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+/// How long a due step may go untaken before the rest of the plan is pushed
+/// back.
+const STRETCH_AFTER: Duration = Duration::hours(12);
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// SETTINGS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// How warm-up plans are generated.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WarmupSettings {
+    /// Whether new wallets warm up at all.
+    pub enabled: bool,
+
+    /// Shortest plan, in days.
+    pub min_days: u32,
+
+    /// Longest plan, in days.
+    pub max_days: u32,
+
+    /// Amount factor at the start of a plan, ramping to 1.0 at its end.
+    pub initial_factor: f64,
+}
+
+impl Default for WarmupSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_days: 3,
+            max_days: 7,
+            initial_factor: 0.1,
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// PLAN
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Kind of a warm-up step.
+///
+/// Each plugin decides what a step means for its protocol; the kinds only
+/// describe the shape of the sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WarmupStepKind {
+    /// A tiny position at a low risk level.
+    Stake,
+    /// A small game bet.
+    Bet,
+    /// Claiming what the position earned.
+    Claim,
+}
+
+/// A step of a warm-up plan.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WarmupStep {
+    /// What the wallet does.
+    pub kind: WarmupStepKind,
+
+    /// When the step becomes due.
+    pub due_at: DateTime<Utc>,
+}
+
+/// A new wallet's warm-up: a sequence of small steps and an amount ramp.
+///
+/// # Example
+///
+/// ```
+/// use chrono::{Duration, Utc};
+/// use fleet_core::wallet::{WarmupPlan, WarmupSettings};
+///
+/// let now = Utc::now();
+/// let plan = WarmupPlan::generate(&WarmupSettings::default(), 42, now);
+///
+/// assert_eq!(plan, WarmupPlan::generate(&WarmupSettings::default(), 42, now));
+/// assert!((plan.factor_at(now) - 0.1).abs() < 1e-9);
+/// assert!((plan.factor_at(plan.ends_at) - 1.0).abs() < 1e-9);
+/// assert!(!plan.is_complete_at(plan.ends_at + Duration::days(1)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WarmupPlan {
+    /// Start of the amount ramp.
+    ///
+    /// Pushed back together with the remaining steps when the plan is
+    /// stretched, so the ramp halts while the wallet is stalled.
+    pub starts_at: DateTime<Utc>,
+
+    /// End of the plan, when the factor reaches 1.0.
+    pub ends_at: DateTime<Utc>,
+
+    /// Amount factor at `starts_at`.
+    pub initial_factor: f64,
+
+    /// The steps, in order.
+    pub steps: Vec<WarmupStep>,
+
+    /// Number of steps taken.
+    pub completed: usize,
+}
+
+impl WarmupPlan {
+    /// Generate a plan starting at `starts_at`.
+    ///
+    /// The same settings, seed and start always give the same plan.
+    #[must_use]
+    pub fn generate(settings: &WarmupSettings, seed: u64, starts_at: DateTime<Utc>) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let min_days = settings.min_days.max(1);
+        let days = rng.random_range(min_days..=settings.max_days.max(min_days));
+        let duration = Duration::days(i64::from(days));
+
+        // Idle gaps of uneven length before each step and after the last one
+        let kinds = Self::sequence(&mut rng);
+        let gaps: Vec<f64> = (0..=kinds.len())
+            .map(|_| rng.random_range(0.5..1.5))
+            .collect();
+        let total: f64 = gaps.iter().sum();
+
+        let mut elapsed = 0.0;
+        let steps = kinds
+            .into_iter()
+            .zip(&gaps)
+            .map(|(kind, gap)| {
+                elapsed += gap / total;
+                WarmupStep {
+                    kind,
+                    due_at: starts_at + scale(duration, elapsed),
+                }
+            })
+            .collect();
+
+        Self {
+            starts_at,
+            ends_at: starts_at + duration,
+            initial_factor: settings.initial_factor.clamp(0.0, 1.0),
+            steps,
+            completed: 0,
+        }
+    }
+
+    /// A shuffled sequence of an entry, two bets and a claim, with the entry
+    /// before the claim.
+    fn sequence(rng: &mut StdRng) -> Vec<WarmupStepKind> {
+        let mut kinds = vec![
+            WarmupStepKind::Bet,
+            WarmupStepKind::Bet,
+            WarmupStepKind::Claim,
+        ];
+        kinds.shuffle(rng);
+        let claim = kinds
+            .iter()
+            .position(|kind| *kind == WarmupStepKind::Claim)
+            .unwrap_or_default();
+        kinds.insert(rng.random_range(0..=claim), WarmupStepKind::Stake);
+        kinds
+    }
+
+    /// Amount factor at `now`.
+    ///
+    /// Ramps linearly from `initial_factor` at `starts_at` to 1.0 at
+    /// `ends_at`.
+    #[must_use]
+    pub fn factor_at(&self, now: DateTime<Utc>) -> f64 {
+        let total = (self.ends_at - self.starts_at).num_seconds();
+        if total <= 0 {
+            return 1.0;
+        }
+        let elapsed = (now - self.starts_at).num_seconds().clamp(0, total);
+        self.initial_factor + (1.0 - self.initial_factor) * (elapsed as f64 / total as f64)
+    }
+
+    /// The next step, if it is due at `now`.
+    #[must_use]
+    pub fn due_step(&self, now: DateTime<Utc>) -> Option<WarmupStepKind> {
+        self.steps
+            .get(self.completed)
+            .filter(|step| step.due_at <= now)
+            .map(|step| step.kind)
+    }
+
+    /// Mark the step due at `now` as taken.
+    ///
+    /// Returns whether a step was due.
+    pub fn complete_due_step(&mut self, now: DateTime<Utc>) -> bool {
+        let due = self.due_step(now).is_some();
+        if due {
+            self.completed += 1;
+        }
+        due
+    }
+
+    /// Push the rest of the plan back if its next step has not been taken in
+    /// time.
+    ///
+    /// A wallet that cannot afford its steps, or finds nothing to act on,
+    /// warms up more slowly rather than failing: once the due step is more
+    /// than 12 hours late, the step becomes due at `now` and the later steps,
+    /// the ramp and the end move by as much. Returns how far the plan moved.
+    pub fn stretch_at(&mut self, now: DateTime<Utc>) -> Duration {
+        let Some(step) = self.steps.get(self.completed) else {
+            return Duration::zero();
+        };
+        let delay = now - step.due_at;
+        if delay <= STRETCH_AFTER {
+            return Duration::zero();
+        }
+
+        for step in &mut self.steps[self.completed..] {
+            step.due_at += delay;
+        }
+        self.starts_at += delay;
+        self.ends_at += delay;
+        delay
+    }
+
+    /// Check whether every step was taken and the plan has ended.
+    #[must_use]
+    pub fn is_complete_at(&self, now: DateTime<Utc>) -> bool {
+        self.completed >= self.steps.len() && now >= self.ends_at
+    }
+}
+
+/// `fraction` of `duration`, to the second.
+#[allow(clippy::cast_possible_truncation)] // Fractions of a plan of days
+fn scale(duration: Duration, fraction: f64) -> Duration {
+    Duration::seconds((duration.num_seconds() as f64 * fraction) as i64)
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings() -> WarmupSettings {
+        WarmupSettings {
+            enabled: true,
+            ..WarmupSettings::default()
+        }
+    }
+
+    #[test]
+    fn plans_are_deterministic_per_seed() {
+        let now = Utc::now();
+        let plan = WarmupPlan::generate(&settings(), 7, now);
+
+        assert_eq!(plan, WarmupPlan::generate(&settings(), 7, now));
+        assert!((0..20).any(|seed| WarmupPlan::generate(&settings(), seed, now) != plan));
+
+        for seed in 0..20 {
+            let plan = WarmupPlan::generate(&settings(), seed, now);
+            let days = (plan.ends_at - plan.starts_at).num_days();
+            assert!((3..=7).contains(&days), "{days} days");
+
+            let kinds: Vec<_> = plan.steps.iter().map(|step| step.kind).collect();
+            let stake = kinds.iter().position(|k| *k == WarmupStepKind::Stake);
+            let claim = kinds.iter().position(|k| *k == WarmupStepKind::Claim);
+            assert!(stake < claim, "{kinds:?}");
+            assert_eq!(kinds.len(), 4);
+
+            assert!(plan.steps.is_sorted_by_key(|step| step.due_at));
+            assert!(plan.steps.iter().all(|step| step.due_at > plan.starts_at));
+            assert!(plan.steps.iter().all(|step| step.due_at < plan.ends_at));
+        }
+    }
+
+    #[test]
+    fn factor_ramps_to_one() {
+        let now = Utc::now();
+        let plan = WarmupPlan {
+            starts_at: now,
+            ends_at: now + Duration::days(4),
+            initial_factor: 0.2,
+            steps: Vec::new(),
+            completed: 0,
+        };
+        let factor = |at| plan.factor_at(at);
+
+        assert!((factor(now - Duration::days(1)) - 0.2).abs() < 1e-9);
+        assert!((factor(now) - 0.2).abs() < 1e-9);
+        assert!((factor(now + Duration::days(1)) - 0.4).abs() < 1e-9);
+        assert!((factor(now + Duration::days(2)) - 0.6).abs() < 1e-9);
+        assert!((factor(now + Duration::days(4)) - 1.0).abs() < 1e-9);
+        assert!((factor(now + Duration::days(9)) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn steps_complete_in_order() {
+        let now = Utc::now();
+        let mut plan = WarmupPlan::generate(&settings(), 3, now);
+        let first = plan.steps[0];
+
+        assert_eq!(plan.due_step(now), None);
+        assert!(!plan.complete_due_step(now));
+        assert_eq!(plan.due_step(first.due_at), Some(first.kind));
+
+        for step in plan.steps.clone() {
+            assert!(plan.complete_due_step(step.due_at));
+        }
+        assert_eq!(plan.due_step(plan.ends_at), None);
+        assert!(!plan.is_complete_at(plan.ends_at - Duration::seconds(1)));
+        assert!(plan.is_complete_at(plan.ends_at));
+    }
+
+    #[test]
+    fn stalled_plans_stretch() {
+        let now = Utc::now();
+        let mut plan = WarmupPlan::generate(&settings(), 3, now);
+        let original = plan.clone();
+        let first = plan.steps[0].due_at;
+
+        // A little late is fine
+        assert_eq!(
+            plan.stretch_at(first + Duration::hours(1)),
+            Duration::zero()
+        );
+        assert_eq!(plan, original);
+
+        // Too late moves the rest of the plan, and holds back the ramp
+        let late = first + Duration::days(2);
+        assert_eq!(plan.stretch_at(late), Duration::days(2));
+        assert_eq!(plan.steps[0].due_at, late);
+        assert_eq!(plan.ends_at, original.ends_at + Duration::days(2));
+        assert!((plan.factor_at(late) - original.factor_at(first)).abs() < 1e-9);
+        assert!(!plan.is_complete_at(original.ends_at));
+
+        // Taken steps stay where they were
+        assert!(plan.complete_due_step(late));
+        let second = plan.steps[1].due_at;
+        plan.stretch_at(second + Duration::days(1));
+        assert_eq!(plan.steps[0].due_at, late);
+        assert_eq!(plan.steps[1].due_at, second + Duration::days(1));
+    }
+}
@@ -0,0 +1,107 @@
+//! ABI bindings for the `ArcadeCore` game registry.
+//!
+//! `ArcadeCore` holds the arcade's shared state:
+//! - The registry of games and their entry limits
+//! - Game sessions and the prize pool behind them
+//! - Pending payouts, pulled by players with `withdrawPayout`
+//!
+//! `play` and `getGames` are the registry's generic entry and listing
+//! points; deployments without them revert, which the fleet treats as an
+//! empty arcade.
+//!
+//! # Solidity Contract
+//!
+//! ```solidity
+//! contract ArcadeCore is IArcadeCore {
+//!     function withdrawPayout() external returns (uint256 amount);
+//!     function getPendingPayout(address player) external view returns (uint256);
+//!     // ... etc
+//! }
+//! ```
+
+use alloy::sol;
+
+sol! {
+    // ═══════════════════════════════════════════════════════════════════════════
+    // FUNCTIONS
+    // ═══════════════════════════════════════════════════════════════════════════
+
+    function play(uint256 gameId, uint256 amount) external;
+    function withdrawPayout() external returns (uint256 amount);
+
+    // ═══════════════════════════════════════════════════════════════════════════
+    // VIEWS
+    // ═══════════════════════════════════════════════════════════════════════════
+
+    /// A registered game and its entry limits.
+    ///
+    /// A `maxEntry` of zero means no limit.
+    #[derive(Debug, PartialEq, Eq)]
+    struct GameListing {
+        uint256 gameId;
+        address game;
+        uint256 minEntry;
+        uint256 maxEntry;
+        uint16 rakeBps;
+        bool paused;
+    }
+
+    function getGames() external view returns (GameListing[] memory);
+    function getPendingPayout(address player) external view returns (uint256);
+
+    // ═══════════════════════════════════════════════════════════════════════════
+    // ERRORS
+    // ═══════════════════════════════════════════════════════════════════════════
+
+    error GameNotRegistered();
+    error GameAlreadyRegistered();
+    error GamePaused();
+    error SessionNotFound();
+    error SessionGameMismatch();
+    error SessionNotActive();
+    error SessionAlreadyExists();
+    error PayoutExceedsPrizePool();
+    error InvalidPayoutAmount();
+    error RefundExceedsDeposit();
+    error InvalidRefundAmount();
+    error AlreadyRefunded();
+    error SessionNotRefundable();
+    error RefundsBlockedAfterPayouts();
+    error NoDepositFound();
+    error InvalidEntryAmount();
+    error PositionRequired();
+    error RateLimited();
+    error ArrayLengthMismatch(
+        uint256 sessionIdsLen,
+        uint256 playersLen,
+        uint256 amountsLen,
+        uint256 burnAmountsLen,
+        uint256 resultsLen
+    );
+    error BatchTooLarge(uint256 size, uint256 maxSize);
+    error EmptyBatch();
+    error GameNotQuarantinable();
+    error InvalidAddress();
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::sol_types::{SolCall, SolError};
+
+    use super::*;
+
+    #[test]
+    fn play_signature() {
+        assert_eq!(playCall::SIGNATURE, "play(uint256,uint256)");
+    }
+
+    #[test]
+    fn withdraw_payout_signature() {
+        assert_eq!(withdrawPayoutCall::SIGNATURE, "withdrawPayout()");
+    }
+
+    #[test]
+    fn batch_too_large_signature() {
+        assert_eq!(BatchTooLarge::SIGNATURE, "BatchTooLarge(uint256,uint256)");
+    }
+}
@@ -93,6 +93,34 @@ sol! {
         address indexed account,
         bool excluded
     );
+
+    // ═══════════════════════════════════════════════════════════════════════════
+    // FUNCTIONS
+    // ═══════════════════════════════════════════════════════════════════════════
+
+    function transfer(address to, uint256 value) external returns (bool);
+    function transferFrom(address from, address to, uint256 value) external returns (bool);
+    function setTaxExclusion(address account, bool excluded) external;
+    function burn(uint256 amount) external;
+    function burnFrom(address from, uint256 amount) external;
+    function approve(address spender, uint256 value) external returns (bool);
+
+    // ═══════════════════════════════════════════════════════════════════════════
+    // VIEWS
+    // ═══════════════════════════════════════════════════════════════════════════
+
+    function balanceOf(address account) external view returns (uint256);
+    function allowance(address owner, address spender) external view returns (uint256);
+    function TAX_RATE_BPS() external view returns (uint16);
+    function isExcludedFromTax(address account) external view returns (bool);
+
+    // ═══════════════════════════════════════════════════════════════════════════
+    // ERRORS
+    // ═══════════════════════════════════════════════════════════════════════════
+
+    error InvalidTreasury();
+    error DistributionLengthMismatch();
+    error DistributionSumMismatch();
 }
 
 #[cfg(test)]
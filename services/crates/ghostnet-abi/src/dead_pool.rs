@@ -100,6 +100,53 @@ sol! {
         address indexed user,
         uint256 amount
     );
+
+    // ═══════════════════════════════════════════════════════════════════════════
+    // FUNCTIONS
+    // ═══════════════════════════════════════════════════════════════════════════
+    // Enum arguments (`RoundType`, `IGhostCore.Level`) are ABI-encoded as `uint8`.
+
+    function createRound(uint8 roundType, uint8 targetLevel, uint256 line, uint64 deadline)
+        external returns (uint256 roundId);
+    function resolveRound(uint256 roundId, bool outcome) external;
+    function placeBet(uint256 roundId, bool isOver, uint256 amount) external;
+    function claimWinnings(uint256 roundId) external returns (uint256 winnings);
+
+    // ═══════════════════════════════════════════════════════════════════════════
+    // VIEWS
+    // ═══════════════════════════════════════════════════════════════════════════
+
+    /// A betting round (`IDeadPool.Round`).
+    #[derive(Debug, PartialEq, Eq)]
+    struct Round {
+        uint8 roundType;
+        uint8 targetLevel;
+        uint256 line;
+        uint256 overPool;
+        uint256 underPool;
+        uint64 deadline;
+        uint64 resolveTime;
+        bool resolved;
+        bool outcome;
+    }
+
+    function getRound(uint256 roundId) external view returns (Round memory);
+    function roundCount() external view returns (uint256);
+
+    // ═══════════════════════════════════════════════════════════════════════════
+    // ERRORS
+    // ═══════════════════════════════════════════════════════════════════════════
+
+    error RoundNotFound();
+    error RoundEnded();
+    error RoundNotEnded();
+    error RoundNotResolved();
+    error RoundAlreadyResolved();
+    error InvalidAmount();
+    error NoBetExists();
+    error AlreadyClaimed();
+    error NotWinner();
+    error NotAuthorized();
 }
 
 #[cfg(test)]
@@ -91,6 +91,26 @@ sol! {
         address indexed to,
         uint256 amount
     );
+
+    // ═══════════════════════════════════════════════════════════════════════════
+    // FUNCTIONS
+    // ═══════════════════════════════════════════════════════════════════════════
+
+    function collectToll(bytes32 reason) external payable;
+    function executeBuyback(uint256 minDataOut) external;
+    function executeBuybackWithData(bytes swapData, uint256 minDataOut) external;
+
+    // ═══════════════════════════════════════════════════════════════════════════
+    // ERRORS
+    // ═══════════════════════════════════════════════════════════════════════════
+
+    error InvalidAddress();
+    error InvalidAmount();
+    error InsufficientBalance();
+    error SwapFailed();
+    error TransferFailed();
+    error TollRequired();
+    error Unauthorized();
 }
 
 #[cfg(test)]
@@ -9,6 +9,9 @@
 //! - System reset (doomsday clock)
 //! - Position culling (capacity management)
 //!
+//! Alongside the events, it declares the entry points and views that the
+//! indexer and the fleet call, and the contract's custom errors.
+//!
 //! # Solidity Contract
 //!
 //! ```solidity
@@ -195,6 +198,117 @@ sol! {
         uint256 returnedAmount,
         address indexed newEntrant
     );
+
+    // ═══════════════════════════════════════════════════════════════════════════
+    // FUNCTIONS
+    // ═══════════════════════════════════════════════════════════════════════════
+    // User and keeper entry points, for matching transaction input by selector
+    // and for building the fleet's calldata. The two `extract` overloads are
+    // `extract_0Call` (everything) and `extract_1Call` (part of the stake).
+    // Enum arguments (`Level`, `BoostType`) are ABI-encoded as `uint8`.
+
+    function jackIn(uint256 amount, uint8 level) external;
+    function addStake(uint256 amount) external;
+    function extract() external returns (uint256 amount, uint256 rewards);
+    // Partial extraction, not offered by every deployment
+    function extract(uint256 amount) external returns (uint256 extracted, uint256 rewards);
+    function claimRewards() external returns (uint256 rewards);
+    function processDeaths(uint8 level, address[] deadUsers) external returns (uint256 totalDead);
+    function distributeCascade(uint8 level, uint256 totalDead) external;
+    function incrementGhostStreak(uint8 level) external;
+    function addEmissionRewards(uint8 level, uint256 amount) external;
+    function applyBoost(
+        uint8 boostType,
+        uint16 valueBps,
+        uint64 expiry,
+        bytes32 nonce,
+        bytes signature
+    ) external;
+    function triggerSystemReset() external;
+
+    // ═══════════════════════════════════════════════════════════════════════════
+    // VIEWS
+    // ═══════════════════════════════════════════════════════════════════════════
+
+    /// Runtime state of a level (`IGhostCore.LevelState`).
+    #[derive(Debug, PartialEq, Eq)]
+    struct LevelState {
+        uint256 totalStaked;
+        uint256 aliveCount;
+        uint256 accRewardsPerShare;
+        uint64 nextScanTime;
+    }
+
+    function getLevelState(uint8 level) external view returns (LevelState memory);
+
+    /// A user's position (`IGhostCore.Position`).
+    ///
+    /// `level` is the `IGhostCore.Level` enum, ABI-encoded as `uint8`.
+    #[derive(Debug, PartialEq, Eq)]
+    struct Position {
+        uint256 amount;
+        uint8 level;
+        uint64 entryTimestamp;
+        uint64 lastAddTimestamp;
+        uint256 rewardDebt;
+        bool alive;
+        uint16 ghostStreak;
+    }
+
+    function getPosition(address user) external view returns (Position memory);
+    function getTotalValueLocked() external view returns (uint256);
+    function getPendingRewards(address user) external view returns (uint256);
+    function getEffectiveDeathRate(address user) external view returns (uint16);
+    function isInLockPeriod(address user) external view returns (bool);
+    function isAlive(address user) external view returns (bool);
+
+    /// An active boost of a user (`IGhostCore.Boost`).
+    ///
+    /// `boostType` is the `IGhostCore.BoostType` enum, ABI-encoded as `uint8`.
+    #[derive(Debug, PartialEq, Eq)]
+    struct Boost {
+        uint8 boostType;
+        uint16 valueBps;
+        uint64 expiry;
+    }
+
+    function getActiveBoosts(address user) external view returns (Boost[] memory);
+
+    /// The doomsday clock (`IGhostCore.SystemReset`).
+    #[derive(Debug, PartialEq, Eq)]
+    struct SystemReset {
+        uint64 deadline;
+        address lastDepositor;
+        uint64 lastDepositTime;
+        uint256 epoch;
+        uint16 penaltyBps;
+    }
+
+    function getSystemReset() external view returns (SystemReset memory);
+    function getCullingRisk(address user) external view returns (
+        uint16 riskBps,
+        bool isEligible,
+        uint16 capacityPct
+    );
+
+    // ═══════════════════════════════════════════════════════════════════════════
+    // ERRORS
+    // ═══════════════════════════════════════════════════════════════════════════
+
+    error InvalidLevel();
+    error InvalidAmount();
+    error PositionAlreadyExists();
+    error NoPositionExists();
+    error PositionDead();
+    error PositionLocked();
+    error LevelMismatch();
+    error BelowMinimumStake();
+    error InvalidSignature();
+    error SignatureExpired();
+    error NonceAlreadyUsed();
+    error LevelAtCapacity();
+    error NotAuthorized();
+    error SystemResetNotReady();
 }
 
 #[cfg(test)]
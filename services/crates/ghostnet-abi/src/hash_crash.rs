@@ -0,0 +1,98 @@
+//! ABI bindings for the `HashCrash` arcade game.
+//!
+//! `HashCrash` is a crash game played through `ArcadeCore`:
+//! - Players bet during a round's betting window with a target multiplier
+//! - A future block hash decides where the multiplier crashes
+//! - Bets whose target is at or below the crash point are paid out
+//!
+//! The indexer does not index `HashCrash`; the fleet places bets and reads
+//! rounds to learn how they ended.
+//!
+//! # Solidity Contract
+//!
+//! ```solidity
+//! contract HashCrash is ArcadeGame {
+//!     function placeBet(uint256 amount, uint256 targetMultiplier) external;
+//!     function getRound(uint256 roundId) external view returns (...);
+//!     // ... etc
+//! }
+//! ```
+
+use alloy::sol;
+
+sol! {
+    // ═══════════════════════════════════════════════════════════════════════════
+    // FUNCTIONS
+    // ═══════════════════════════════════════════════════════════════════════════
+    // Multipliers are in hundredths (250 = 2.50x).
+
+    function placeBet(uint256 amount, uint256 targetMultiplier) external;
+
+    // ═══════════════════════════════════════════════════════════════════════════
+    // VIEWS
+    // ═══════════════════════════════════════════════════════════════════════════
+    // `state` is the `IArcadeTypes.SessionState` enum, ABI-encoded as `uint8`.
+
+    function getCurrentRound() external view returns (
+        uint256 roundId,
+        uint8 state,
+        uint64 bettingEndsAt,
+        uint256 seedBlock,
+        uint256 crashMultiplier,
+        uint256 totalPrizePool,
+        uint256 playerCount
+    );
+    function getRound(uint256 roundId) external view returns (
+        uint8 state,
+        uint64 bettingEndTime,
+        uint256 prizePool,
+        uint256 crashMultiplier,
+        uint256 totalPaidOut,
+        uint256 playerCount
+    );
+    function getPlayerBet(uint256 roundId, address player) external view returns (
+        uint256 amount,
+        uint256 grossAmount,
+        uint256 targetMultiplier,
+        bool settled
+    );
+
+    // ═══════════════════════════════════════════════════════════════════════════
+    // ERRORS
+    // ═══════════════════════════════════════════════════════════════════════════
+
+    error BettingClosed();
+    error NoBetPlaced();
+    error AlreadySettled();
+    error NotRevealed();
+    error InvalidTargetMultiplier();
+    error RoundFull();
+    error RoundNotReady();
+    error RoundInProgress();
+    error BettingNotEnded();
+    error InvalidArcadeCore();
+    error InvalidAddress();
+    error ZeroBetAmount();
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::sol_types::{SolCall, SolError};
+
+    use super::*;
+
+    #[test]
+    fn place_bet_signature() {
+        assert_eq!(placeBetCall::SIGNATURE, "placeBet(uint256,uint256)");
+    }
+
+    #[test]
+    fn get_player_bet_signature() {
+        assert_eq!(getPlayerBetCall::SIGNATURE, "getPlayerBet(uint256,address)");
+    }
+
+    #[test]
+    fn betting_closed_signature() {
+        assert_eq!(BettingClosed::SIGNATURE, "BettingClosed()");
+    }
+}
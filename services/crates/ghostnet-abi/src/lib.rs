@@ -0,0 +1,279 @@
+//! ABI bindings for GHOSTNET smart contracts.
+//!
+//! This crate provides type-safe Rust bindings for Solidity events, functions
+//! and custom errors using the `alloy::sol!` macro. Each contract has its own
+//! submodule with its definitions.
+//!
+//! It is the only place the GHOSTNET ABIs are declared: the indexer (as
+//! `ghostnet_indexer::abi`) decodes events and matches calls with these
+//! types, and the fleet's GHOSTNET plugin encodes its calls and decodes its
+//! views and receipts with them, so the two cannot drift apart.
+//!
+//! # Architecture
+//!
+//! ```text
+//! ┌─────────────────────────────────────────────────────────────────┐
+//! │                       ABI Bindings Layer                        │
+//! │                                                                 │
+//! │  ┌─────────────┐  ┌─────────────┐  ┌─────────────┐             │
+//! │  │ ghost_core  │  │ trace_scan  │  │  dead_pool  │             │
+//! │  │  10 events  │  │  3 events   │  │  4 events   │             │
+//! │  └─────────────┘  └─────────────┘  └─────────────┘             │
+//! │                                                                 │
+//! │  ┌─────────────┐  ┌─────────────┐  ┌─────────────────────────┐ │
+//! │  │ data_token  │  │ fee_router  │  │ rewards_distributor     │ │
+//! │  │  4 events   │  │  3 events   │  │ 3 events (incl vesting) │ │
+//! │  └─────────────┘  └─────────────┘  └─────────────────────────┘ │
+//! │                                                                 │
+//! │  ┌─────────────┐  ┌─────────────┐  ┌─────────────────────────┐ │
+//! │  │ hash_crash  │  │ arcade_core │  │ selectors               │ │
+//! │  │  calls only │  │  calls only │  │ selector / topic tables │ │
+//! │  └─────────────┘  └─────────────┘  └─────────────────────────┘ │
+//! └─────────────────────────────────────────────────────────────────┘
+//! ```
+//!
+//! # Usage
+//!
+//! Events are decoded from raw logs using the `SolEvent` trait:
+//!
+//! ```ignore
+//! use alloy::sol_types::SolEvent;
+//! use ghostnet_abi::ghost_core::JackedIn;
+//!
+//! // Decode from a raw log
+//! let event = JackedIn::decode_log(&log.inner)?;
+//! println!("User {} jacked in at level {}", event.user, event.level);
+//! ```
+//!
+//! Each module also declares the contract's state-changing functions, so
+//! calldata can be encoded (`ghost_core::jackInCall { .. }.abi_encode()`, as
+//! the fleet does) and transaction input matched by selector. Views are
+//! declared alongside (`ghost_core::getLevelStateCall`, read by the
+//! indexer's `ScanPredictor`; `ghost_core::getActiveBoostsCall`, read by the
+//! fleet), and so are the custom errors a call can revert with
+//! (`ghost_core::PositionLocked`).
+//!
+//! For raw bytes, [`selectors`] lists every function selector, error
+//! selector and event topic with its contract and signature.
+//!
+//! # Contract Event Mapping
+//!
+//! | Contract | Module | Event Count | Description |
+//! |----------|--------|-------------|-------------|
+//! | `GhostCore` | [`ghost_core`] | 10 | Position lifecycle, boosts, system reset |
+//! | `TraceScan` | [`trace_scan`] | 3 | Scan execution and finalization |
+//! | `DeadPool` | [`dead_pool`] | 4 | Prediction market rounds and bets |
+//! | `DataToken` | [`data_token`] | 4 | ERC20 transfers and tax events |
+//! | `FeeRouter` | [`fee_router`] | 3 | Fee collection and buybacks |
+//! | `RewardsDistributor` | [`rewards_distributor`] | 3 | Emissions and team vesting |
+//! | `HashCrash` | [`hash_crash`] | 0 | Crash game bets and rounds |
+//! | `ArcadeCore` | [`arcade_core`] | 0 | Game registry and payouts |
+
+pub mod arcade_core;
+pub mod data_token;
+pub mod dead_pool;
+pub mod fee_router;
+pub mod ghost_core;
+pub mod hash_crash;
+pub mod rewards_distributor;
+pub mod selectors;
+pub mod trace_scan;
+
+// Re-export all event types for convenience
+pub use data_token::{TaxBurned, TaxCollected, TaxExclusionSet, Transfer};
+pub use dead_pool::{BetPlaced, RoundCreated, RoundResolved, WinningsClaimed};
+pub use fee_router::{BuybackExecuted, OperationsWithdrawn, TollCollected};
+pub use ghost_core::{
+    BoostApplied, CascadeDistributed, DeathsProcessed, EmissionsAdded, Extracted, JackedIn,
+    PositionCulled, StakeAdded, SurvivorsUpdated, SystemResetTriggered,
+};
+pub use rewards_distributor::{EmissionsDistributed, TokensClaimed, WeightsUpdated};
+pub use trace_scan::{DeathsSubmitted, ScanExecuted, ScanFinalized};
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use alloy::sol_types::SolEvent;
+
+    use super::*;
+
+    /// Verify that all event signature hashes are unique (no collisions).
+    #[test]
+    fn event_signatures_are_unique() {
+        let signatures = [
+            // GhostCore
+            JackedIn::SIGNATURE_HASH,
+            StakeAdded::SIGNATURE_HASH,
+            Extracted::SIGNATURE_HASH,
+            DeathsProcessed::SIGNATURE_HASH,
+            SurvivorsUpdated::SIGNATURE_HASH,
+            CascadeDistributed::SIGNATURE_HASH,
+            EmissionsAdded::SIGNATURE_HASH,
+            BoostApplied::SIGNATURE_HASH,
+            SystemResetTriggered::SIGNATURE_HASH,
+            PositionCulled::SIGNATURE_HASH,
+            // TraceScan
+            ScanExecuted::SIGNATURE_HASH,
+            DeathsSubmitted::SIGNATURE_HASH,
+            ScanFinalized::SIGNATURE_HASH,
+            // DeadPool
+            RoundCreated::SIGNATURE_HASH,
+            BetPlaced::SIGNATURE_HASH,
+            RoundResolved::SIGNATURE_HASH,
+            WinningsClaimed::SIGNATURE_HASH,
+            // DataToken
+            Transfer::SIGNATURE_HASH,
+            TaxBurned::SIGNATURE_HASH,
+            TaxCollected::SIGNATURE_HASH,
+            TaxExclusionSet::SIGNATURE_HASH,
+            // FeeRouter
+            TollCollected::SIGNATURE_HASH,
+            BuybackExecuted::SIGNATURE_HASH,
+            OperationsWithdrawn::SIGNATURE_HASH,
+            // RewardsDistributor
+            EmissionsDistributed::SIGNATURE_HASH,
+            WeightsUpdated::SIGNATURE_HASH,
+            TokensClaimed::SIGNATURE_HASH,
+        ];
+
+        let mut seen = std::collections::HashSet::new();
+        for sig in signatures {
+            assert!(
+                seen.insert(sig),
+                "Duplicate event signature hash detected: {sig:?}"
+            );
+        }
+
+        // Verify we have the expected count (27 events total)
+        assert_eq!(seen.len(), 27, "Expected 27 unique event signatures");
+    }
+
+    /// Verify event signature strings match expected Solidity signatures.
+    #[test]
+    fn event_signature_strings() {
+        // Spot-check a few critical events
+        assert_eq!(
+            JackedIn::SIGNATURE,
+            "JackedIn(address,uint256,uint8,uint256)"
+        );
+        assert_eq!(Transfer::SIGNATURE, "Transfer(address,address,uint256)");
+        assert_eq!(
+            ScanExecuted::SIGNATURE,
+            "ScanExecuted(uint8,uint256,uint256,uint64)"
+        );
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════════
+    // GOLDEN ENCODINGS
+    // ═══════════════════════════════════════════════════════════════════════════
+    // Hex below is what the deployed contracts expect and emit; a change to a
+    // declaration that alters it is a change to the ABI, not a refactor.
+
+    #[test]
+    fn golden_calldata() {
+        use alloy::primitives::{Address, U256, hex};
+        use alloy::sol_types::SolCall;
+
+        let jack_in = ghost_core::jackInCall {
+            amount: U256::from(1000),
+            level: 3,
+        };
+        assert_eq!(
+            jack_in.abi_encode(),
+            hex!(
+                "440ad4e2"
+                "00000000000000000000000000000000000000000000000000000000000003e8"
+                "0000000000000000000000000000000000000000000000000000000000000003"
+            )
+        );
+
+        assert_eq!(ghost_core::extract_0Call {}.abi_encode(), hex!("1e83cdab"));
+        assert_eq!(
+            ghost_core::extract_1Call {
+                amount: U256::from(5)
+            }
+            .abi_encode(),
+            hex!(
+                "85b39782"
+                "0000000000000000000000000000000000000000000000000000000000000005"
+            )
+        );
+        assert_eq!(
+            ghost_core::claimRewardsCall {}.abi_encode(),
+            hex!("372500ab")
+        );
+
+        let approve = data_token::approveCall {
+            spender: Address::repeat_byte(0x05),
+            value: U256::MAX,
+        };
+        assert_eq!(
+            approve.abi_encode(),
+            hex!(
+                "095ea7b3"
+                "0000000000000000000000000505050505050505050505050505050505050505"
+                "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff"
+            )
+        );
+
+        let bet = hash_crash::placeBetCall {
+            amount: U256::from(100),
+            targetMultiplier: U256::from(250),
+        };
+        assert_eq!(
+            bet.abi_encode(),
+            hex!(
+                "4afe62b5"
+                "0000000000000000000000000000000000000000000000000000000000000064"
+                "00000000000000000000000000000000000000000000000000000000000000fa"
+            )
+        );
+    }
+
+    #[test]
+    fn golden_revert_data() {
+        use alloy::primitives::hex;
+        use alloy::sol_types::SolError;
+
+        assert_eq!(ghost_core::PositionLocked {}.abi_encode(), hex!("c7d26d72"));
+        let known = selectors::revert_error(&hex!("c7d26d72")).unwrap();
+        assert_eq!(
+            (known.contract, known.name()),
+            ("GhostCore", "PositionLocked")
+        );
+    }
+
+    #[test]
+    fn golden_jacked_in_log() {
+        use alloy::primitives::{Address, B256, Log, U256, b256, hex};
+
+        assert_eq!(
+            JackedIn::SIGNATURE_HASH,
+            b256!("cd710317443d3cfc48685d02e7e7f95d996431198935dfb77503c299d1dd2c73")
+        );
+
+        let log = Log::new_unchecked(
+            Address::repeat_byte(0x01),
+            vec![
+                JackedIn::SIGNATURE_HASH,
+                b256!("000000000000000000000000aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"),
+                B256::with_last_byte(3),
+            ],
+            hex!(
+                "00000000000000000000000000000000000000000000000000000000000003e8"
+                "0000000000000000000000000000000000000000000000000000000000000bb8"
+            )
+            .into(),
+        );
+        let event = JackedIn::decode_log(&log).unwrap().data;
+        assert_eq!(
+            event,
+            JackedIn {
+                user: Address::repeat_byte(0xaa),
+                amount: U256::from(1000),
+                level: 3,
+                newTotal: U256::from(3000),
+            }
+        );
+    }
+}
@@ -76,6 +76,30 @@ sol! {
         address indexed beneficiary,
         uint256 amount
     );
+
+    // ═══════════════════════════════════════════════════════════════════════════
+    // FUNCTIONS
+    // ═══════════════════════════════════════════════════════════════════════════
+    // `distribute` and `setLevelWeights` are `RewardsDistributor`'s, `claim` is
+    // `TeamVesting`'s.
+
+    function distribute() external;
+    function setLevelWeights(uint16[5] newWeights) external;
+    function claim() external returns (uint256 amount);
+
+    // ═══════════════════════════════════════════════════════════════════════════
+    // ERRORS
+    // ═══════════════════════════════════════════════════════════════════════════
+    // `InvalidAddress` is shared by both contracts, the rest are
+    // `RewardsDistributor`'s and then `TeamVesting`'s.
+
+    error InvalidAddress();
+    error InvalidWeights();
+    error NothingToDistribute();
+    error DistributionEnded();
+    error ArrayLengthMismatch();
+    error NoVestingSchedule();
+    error NothingToClaim();
 }
 
 #[cfg(test)]
@@ -0,0 +1,328 @@
+//! Tables of the known selectors and topics of the GHOSTNET contracts.
+//!
+//! The bindings in the contract modules are typed; these tables are the
+//! untyped view of the same declarations, for matching raw bytes: the
+//! selector of a transaction's input, the first four bytes of revert data,
+//! or a log's first topic.
+//!
+//! ```text
+//! FUNCTIONS   selector → GhostCore.jackIn(uint256,uint8)     (entry points)
+//! ERRORS      selector → GhostCore.PositionLocked()           (custom errors)
+//! EVENTS      topic0   → GhostCore.JackedIn(address,...)      (events)
+//! ```
+//!
+//! Only state-changing functions are listed: views are never the input of a
+//! transaction, and `DeadPool.getRound` and `HashCrash.getRound` share a
+//! selector. Errors declared by several contracts (`InvalidAddress`,
+//! `InvalidAmount`, ...) have one selector and are listed once, under the
+//! first contract that declares them.
+
+use alloy::primitives::{B256, Selector};
+use alloy::sol_types::{SolCall, SolError, SolEvent};
+
+use crate::{
+    arcade_core, data_token, dead_pool, fee_router, ghost_core, hash_crash, rewards_distributor,
+    trace_scan,
+};
+
+/// A function, error or event of a GHOSTNET contract, keyed by its selector
+/// or topic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Known<T> {
+    /// 4-byte selector, or the 32-byte topic of an event.
+    pub id: T,
+
+    /// Contract that declares it, e.g. `"GhostCore"`.
+    pub contract: &'static str,
+
+    /// Solidity signature, e.g. `"jackIn(uint256,uint8)"`.
+    pub signature: &'static str,
+}
+
+impl<T> Known<T> {
+    /// Name without the parameter list, e.g. `"jackIn"`.
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        self.signature.split('(').next().unwrap_or(self.signature)
+    }
+}
+
+/// A known function, keyed by its selector.
+pub type KnownFunction = Known<[u8; 4]>;
+
+/// A known custom error, keyed by its selector.
+pub type KnownError = Known<[u8; 4]>;
+
+/// A known event, keyed by its topic.
+pub type KnownEvent = Known<B256>;
+
+const fn call<C: SolCall>(contract: &'static str) -> KnownFunction {
+    Known {
+        id: C::SELECTOR,
+        contract,
+        signature: C::SIGNATURE,
+    }
+}
+
+const fn error<E: SolError>(contract: &'static str) -> KnownError {
+    Known {
+        id: E::SELECTOR,
+        contract,
+        signature: E::SIGNATURE,
+    }
+}
+
+const fn log<E: SolEvent>(contract: &'static str) -> KnownEvent {
+    Known {
+        id: E::SIGNATURE_HASH,
+        contract,
+        signature: E::SIGNATURE,
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TABLES
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Every state-changing function of the GHOSTNET contracts.
+pub const FUNCTIONS: &[KnownFunction] = &[
+    call::<ghost_core::jackInCall>("GhostCore"),
+    call::<ghost_core::addStakeCall>("GhostCore"),
+    call::<ghost_core::extract_0Call>("GhostCore"),
+    call::<ghost_core::extract_1Call>("GhostCore"),
+    call::<ghost_core::claimRewardsCall>("GhostCore"),
+    call::<ghost_core::processDeathsCall>("GhostCore"),
+    call::<ghost_core::distributeCascadeCall>("GhostCore"),
+    call::<ghost_core::incrementGhostStreakCall>("GhostCore"),
+    call::<ghost_core::addEmissionRewardsCall>("GhostCore"),
+    call::<ghost_core::applyBoostCall>("GhostCore"),
+    call::<ghost_core::triggerSystemResetCall>("GhostCore"),
+    call::<trace_scan::executeScanCall>("TraceScan"),
+    call::<trace_scan::submitDeathsCall>("TraceScan"),
+    call::<trace_scan::finalizeScanCall>("TraceScan"),
+    call::<dead_pool::createRoundCall>("DeadPool"),
+    call::<dead_pool::resolveRoundCall>("DeadPool"),
+    call::<dead_pool::placeBetCall>("DeadPool"),
+    call::<dead_pool::claimWinningsCall>("DeadPool"),
+    call::<data_token::transferCall>("DataToken"),
+    call::<data_token::transferFromCall>("DataToken"),
+    call::<data_token::approveCall>("DataToken"),
+    call::<data_token::setTaxExclusionCall>("DataToken"),
+    call::<data_token::burnCall>("DataToken"),
+    call::<data_token::burnFromCall>("DataToken"),
+    call::<fee_router::collectTollCall>("FeeRouter"),
+    call::<fee_router::executeBuybackCall>("FeeRouter"),
+    call::<fee_router::executeBuybackWithDataCall>("FeeRouter"),
+    call::<rewards_distributor::distributeCall>("RewardsDistributor"),
+    call::<rewards_distributor::setLevelWeightsCall>("RewardsDistributor"),
+    call::<rewards_distributor::claimCall>("TeamVesting"),
+    call::<hash_crash::placeBetCall>("HashCrash"),
+    call::<arcade_core::playCall>("ArcadeCore"),
+    call::<arcade_core::withdrawPayoutCall>("ArcadeCore"),
+];
+
+/// Every custom error of the GHOSTNET contracts.
+pub const ERRORS: &[KnownError] = &[
+    error::<ghost_core::InvalidLevel>("GhostCore"),
+    error::<ghost_core::InvalidAmount>("GhostCore"),
+    error::<ghost_core::PositionAlreadyExists>("GhostCore"),
+    error::<ghost_core::NoPositionExists>("GhostCore"),
+    error::<ghost_core::PositionDead>("GhostCore"),
+    error::<ghost_core::PositionLocked>("GhostCore"),
+    error::<ghost_core::LevelMismatch>("GhostCore"),
+    error::<ghost_core::BelowMinimumStake>("GhostCore"),
+    error::<ghost_core::InvalidSignature>("GhostCore"),
+    error::<ghost_core::SignatureExpired>("GhostCore"),
+    error::<ghost_core::NonceAlreadyUsed>("GhostCore"),
+    error::<ghost_core::LevelAtCapacity>("GhostCore"),
+    error::<ghost_core::NotAuthorized>("GhostCore"),
+    error::<ghost_core::SystemResetNotReady>("GhostCore"),
+    error::<trace_scan::ScanNotReady>("TraceScan"),
+    error::<trace_scan::ScanAlreadyActive>("TraceScan"),
+    error::<trace_scan::ScanNotActive>("TraceScan"),
+    error::<trace_scan::ScanAlreadyFinalized>("TraceScan"),
+    error::<trace_scan::SubmissionWindowClosed>("TraceScan"),
+    error::<trace_scan::SubmissionWindowNotClosed>("TraceScan"),
+    error::<trace_scan::UserNotDead>("TraceScan"),
+    error::<trace_scan::UserAlreadyProcessed>("TraceScan"),
+    error::<trace_scan::BatchTooLarge>("TraceScan"),
+    error::<dead_pool::RoundNotFound>("DeadPool"),
+    error::<dead_pool::RoundEnded>("DeadPool"),
+    error::<dead_pool::RoundNotEnded>("DeadPool"),
+    error::<dead_pool::RoundNotResolved>("DeadPool"),
+    error::<dead_pool::RoundAlreadyResolved>("DeadPool"),
+    error::<dead_pool::NoBetExists>("DeadPool"),
+    error::<dead_pool::AlreadyClaimed>("DeadPool"),
+    error::<dead_pool::NotWinner>("DeadPool"),
+    error::<data_token::InvalidTreasury>("DataToken"),
+    error::<data_token::DistributionLengthMismatch>("DataToken"),
+    error::<data_token::DistributionSumMismatch>("DataToken"),
+    error::<fee_router::InvalidAddress>("FeeRouter"),
+    error::<fee_router::InsufficientBalance>("FeeRouter"),
+    error::<fee_router::SwapFailed>("FeeRouter"),
+    error::<fee_router::TransferFailed>("FeeRouter"),
+    error::<fee_router::TollRequired>("FeeRouter"),
+    error::<fee_router::Unauthorized>("FeeRouter"),
+    error::<rewards_distributor::InvalidWeights>("RewardsDistributor"),
+    error::<rewards_distributor::NothingToDistribute>("RewardsDistributor"),
+    error::<rewards_distributor::DistributionEnded>("RewardsDistributor"),
+    error::<rewards_distributor::ArrayLengthMismatch>("TeamVesting"),
+    error::<rewards_distributor::NoVestingSchedule>("TeamVesting"),
+    error::<rewards_distributor::NothingToClaim>("TeamVesting"),
+    error::<hash_crash::BettingClosed>("HashCrash"),
+    error::<hash_crash::NoBetPlaced>("HashCrash"),
+    error::<hash_crash::AlreadySettled>("HashCrash"),
+    error::<hash_crash::NotRevealed>("HashCrash"),
+    error::<hash_crash::InvalidTargetMultiplier>("HashCrash"),
+    error::<hash_crash::RoundFull>("HashCrash"),
+    error::<hash_crash::RoundNotReady>("HashCrash"),
+    error::<hash_crash::RoundInProgress>("HashCrash"),
+    error::<hash_crash::BettingNotEnded>("HashCrash"),
+    error::<hash_crash::InvalidArcadeCore>("HashCrash"),
+    error::<hash_crash::ZeroBetAmount>("HashCrash"),
+    error::<arcade_core::GameNotRegistered>("ArcadeCore"),
+    error::<arcade_core::GameAlreadyRegistered>("ArcadeCore"),
+    error::<arcade_core::GamePaused>("ArcadeCore"),
+    error::<arcade_core::SessionNotFound>("ArcadeCore"),
+    error::<arcade_core::SessionGameMismatch>("ArcadeCore"),
+    error::<arcade_core::SessionNotActive>("ArcadeCore"),
+    error::<arcade_core::SessionAlreadyExists>("ArcadeCore"),
+    error::<arcade_core::PayoutExceedsPrizePool>("ArcadeCore"),
+    error::<arcade_core::InvalidPayoutAmount>("ArcadeCore"),
+    error::<arcade_core::RefundExceedsDeposit>("ArcadeCore"),
+    error::<arcade_core::InvalidRefundAmount>("ArcadeCore"),
+    error::<arcade_core::AlreadyRefunded>("ArcadeCore"),
+    error::<arcade_core::SessionNotRefundable>("ArcadeCore"),
+    error::<arcade_core::RefundsBlockedAfterPayouts>("ArcadeCore"),
+    error::<arcade_core::NoDepositFound>("ArcadeCore"),
+    error::<arcade_core::InvalidEntryAmount>("ArcadeCore"),
+    error::<arcade_core::PositionRequired>("ArcadeCore"),
+    error::<arcade_core::RateLimited>("ArcadeCore"),
+    error::<arcade_core::ArrayLengthMismatch>("ArcadeCore"),
+    error::<arcade_core::BatchTooLarge>("ArcadeCore"),
+    error::<arcade_core::EmptyBatch>("ArcadeCore"),
+    error::<arcade_core::GameNotQuarantinable>("ArcadeCore"),
+];
+
+/// Every event of the GHOSTNET contracts.
+pub const EVENTS: &[KnownEvent] = &[
+    log::<ghost_core::JackedIn>("GhostCore"),
+    log::<ghost_core::StakeAdded>("GhostCore"),
+    log::<ghost_core::Extracted>("GhostCore"),
+    log::<ghost_core::DeathsProcessed>("GhostCore"),
+    log::<ghost_core::SurvivorsUpdated>("GhostCore"),
+    log::<ghost_core::CascadeDistributed>("GhostCore"),
+    log::<ghost_core::EmissionsAdded>("GhostCore"),
+    log::<ghost_core::BoostApplied>("GhostCore"),
+    log::<ghost_core::SystemResetTriggered>("GhostCore"),
+    log::<ghost_core::PositionCulled>("GhostCore"),
+    log::<trace_scan::ScanExecuted>("TraceScan"),
+    log::<trace_scan::DeathsSubmitted>("TraceScan"),
+    log::<trace_scan::ScanFinalized>("TraceScan"),
+    log::<dead_pool::RoundCreated>("DeadPool"),
+    log::<dead_pool::BetPlaced>("DeadPool"),
+    log::<dead_pool::RoundResolved>("DeadPool"),
+    log::<dead_pool::WinningsClaimed>("DeadPool"),
+    log::<data_token::Transfer>("DataToken"),
+    log::<data_token::TaxBurned>("DataToken"),
+    log::<data_token::TaxCollected>("DataToken"),
+    log::<data_token::TaxExclusionSet>("DataToken"),
+    log::<fee_router::TollCollected>("FeeRouter"),
+    log::<fee_router::BuybackExecuted>("FeeRouter"),
+    log::<fee_router::OperationsWithdrawn>("FeeRouter"),
+    log::<rewards_distributor::EmissionsDistributed>("RewardsDistributor"),
+    log::<rewards_distributor::WeightsUpdated>("RewardsDistributor"),
+    log::<rewards_distributor::TokensClaimed>("TeamVesting"),
+];
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// LOOKUPS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Look up the function a transaction's input selector calls.
+#[must_use]
+pub fn function(selector: Selector) -> Option<&'static KnownFunction> {
+    FUNCTIONS.iter().find(|known| known.id == selector.0)
+}
+
+/// Look up the custom error of revert data, by its first four bytes.
+#[must_use]
+pub fn revert_error(data: &[u8]) -> Option<&'static KnownError> {
+    let selector = data.get(..4)?;
+    ERRORS.iter().find(|known| known.id.as_slice() == selector)
+}
+
+/// Look up the event of a log's first topic.
+#[must_use]
+pub fn event(topic: B256) -> Option<&'static KnownEvent> {
+    EVENTS.iter().find(|known| known.id == topic)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    fn assert_unique<T: std::hash::Hash + Eq + std::fmt::Debug>(table: &[Known<T>]) {
+        let mut seen = HashSet::new();
+        for known in table {
+            assert!(
+                seen.insert(&known.id),
+                "{}.{} collides with another entry",
+                known.contract,
+                known.signature
+            );
+        }
+    }
+
+    #[test]
+    fn tables_have_no_collisions() {
+        assert_unique(FUNCTIONS);
+        assert_unique(ERRORS);
+        assert_unique(EVENTS);
+
+        assert_eq!(FUNCTIONS.len(), 33);
+        assert_eq!(ERRORS.len(), 79);
+        assert_eq!(EVENTS.len(), 27);
+    }
+
+    #[test]
+    fn shared_errors_share_selectors() {
+        // Listed once, so these must really be the same error
+        assert_eq!(
+            fee_router::InvalidAddress::SELECTOR,
+            arcade_core::InvalidAddress::SELECTOR
+        );
+        assert_eq!(
+            ghost_core::InvalidAmount::SELECTOR,
+            dead_pool::InvalidAmount::SELECTOR
+        );
+        assert_eq!(
+            ghost_core::InvalidLevel::SELECTOR,
+            trace_scan::InvalidLevel::SELECTOR
+        );
+        // Same name, different parameters
+        assert_ne!(
+            trace_scan::BatchTooLarge::SELECTOR,
+            arcade_core::BatchTooLarge::SELECTOR
+        );
+    }
+
+    #[test]
+    fn lookups() {
+        let jack_in = function(ghost_core::jackInCall::SELECTOR.into()).unwrap();
+        assert_eq!(jack_in.contract, "GhostCore");
+        assert_eq!(jack_in.name(), "jackIn");
+        assert_eq!(function(Selector::ZERO), None);
+
+        let locked = revert_error(&ghost_core::PositionLocked {}.abi_encode()).unwrap();
+        assert_eq!(locked.signature, "PositionLocked()");
+        assert_eq!(revert_error(&[0xc7, 0xd2]), None);
+
+        let jacked_in = event(ghost_core::JackedIn::SIGNATURE_HASH).unwrap();
+        assert_eq!(jacked_in.name(), "JackedIn");
+        assert_eq!(event(B256::ZERO), None);
+    }
+}
@@ -90,6 +90,30 @@ sol! {
         uint256 totalDead,
         uint64 finalizedAt
     );
+
+    // ═══════════════════════════════════════════════════════════════════════════
+    // FUNCTIONS
+    // ═══════════════════════════════════════════════════════════════════════════
+    // Keeper entry points; `level` is the `IGhostCore.Level` enum as `uint8`.
+
+    function executeScan(uint8 level) external;
+    function submitDeaths(uint8 level, address[] deadUsers) external;
+    function finalizeScan(uint8 level) external;
+
+    // ═══════════════════════════════════════════════════════════════════════════
+    // ERRORS
+    // ═══════════════════════════════════════════════════════════════════════════
+
+    error ScanNotReady();
+    error ScanAlreadyActive();
+    error ScanNotActive();
+    error ScanAlreadyFinalized();
+    error SubmissionWindowClosed();
+    error SubmissionWindowNotClosed();
+    error UserNotDead();
+    error UserAlreadyProcessed();
+    error BatchTooLarge();
+    error InvalidLevel();
 }
 
 #[cfg(test)]
@@ -10,6 +10,10 @@
 //! - **Realtime API**: Submit transactions and get receipts in ~10ms via
 //!   `realtime_sendRawTransaction`
 //! - **Graceful fallback**: Detect when extended methods aren't available
+//! - **Guarded requests**: Per-method timeouts, response size limits, optional
+//!   retries, and errors that name the method, attempt and elapsed time
+//! - **Bounded concurrency**: A cap on requests in flight, lower for log
+//!   queries, and a bounded queue that sheds load once full (see [`crate::limiter`])
 //!
 //! # Example
 //!
@@ -30,18 +34,33 @@
 //! ```
 
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
 
 use alloy::primitives::{Address, Bytes};
 use alloy::rpc::types::Log;
+use serde::Deserialize;
 use tracing::{debug, info, instrument, warn};
 
-use crate::config::ClientConfig;
+use crate::config::{ClientConfig, RETRY_BASE_DELAY};
 use crate::error::{MegaEthError, Result};
+use crate::limiter::{ClientStats, RequestLimiter};
 use crate::types::{
     FetchStats, JsonRpcRequest, JsonRpcResponse, LogsWithCursorFilter, LogsWithCursorResponse,
     RealtimeResponse,
 };
 
+/// Cursor-paginated log query method.
+const GET_LOGS_WITH_CURSOR: &str = "eth_getLogsWithCursor";
+
+/// Realtime transaction submission method.
+const REALTIME_SEND_RAW_TRANSACTION: &str = "realtime_sendRawTransaction";
+
+/// Most times one log query narrows its block range before giving up.
+const MAX_RANGE_SPLITS: usize = 16;
+
+/// Most times one log query restarts after its cursor expired.
+const MAX_CURSOR_RESTARTS: usize = 3;
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // MEGAETH RPC CLIENT
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -65,7 +84,9 @@ use crate::types::{
 /// # Thread Safety
 ///
 /// This client is `Send + Sync` and can be shared across tasks. The internal
-/// `reqwest::Client` is designed for concurrent use.
+/// `reqwest::Client` is designed for concurrent use, and at most
+/// [`ClientConfig::max_in_flight`] requests are in flight at once; see
+/// [`stats`](Self::stats) for how busy the endpoint is kept.
 ///
 /// # Example
 ///
@@ -90,6 +111,9 @@ pub struct MegaEthClient {
 
     /// Client configuration.
     config: ClientConfig,
+
+    /// Permits of requests in flight.
+    limiter: RequestLimiter,
 }
 
 impl MegaEthClient {
@@ -148,6 +172,7 @@ impl MegaEthClient {
             client,
             rpc_url: rpc_url.into(),
             request_id: AtomicU64::new(1),
+            limiter: RequestLimiter::new(&config),
             config,
         })
     }
@@ -164,6 +189,19 @@ impl MegaEthClient {
         &self.config
     }
 
+    /// Statistics of the requests sent to this client's endpoint so far.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let stats = client.stats();
+    /// println!("{} requests shed, p95 queue wait {:?}", stats.shed, stats.queue_wait_p95);
+    /// ```
+    #[must_use]
+    pub fn stats(&self) -> ClientStats {
+        self.limiter.stats()
+    }
+
     /// Get the next request ID for JSON-RPC correlation.
     fn next_request_id(&self) -> u64 {
         self.request_id.fetch_add(1, Ordering::Relaxed)
@@ -215,6 +253,16 @@ impl MegaEthClient {
     /// This method automatically handles pagination, making multiple requests
     /// as needed to retrieve all logs in the specified range.
     ///
+    /// Server-side limits are worked around as well:
+    ///
+    /// - On [`BlockRangeTooLarge`](MegaEthError::BlockRangeTooLarge) or
+    ///   [`ResourceLimitExceeded`](MegaEthError::ResourceLimitExceeded), the
+    ///   range is halved, or narrowed to what the server allows, and the rest
+    ///   is fetched in ranges of that size, up to 16 times per call.
+    /// - On [`CursorExpired`](MegaEthError::CursorExpired), the query starts
+    ///   over without a cursor from the last block whose logs may be
+    ///   incomplete, up to 3 times per call.
+    ///
     /// # Arguments
     ///
     /// * `from_block` - Starting block number (inclusive)
@@ -230,6 +278,8 @@ impl MegaEthClient {
     /// - [`MegaEthError::MethodNotSupported`] if the endpoint doesn't support cursor pagination
     /// - [`MegaEthError::CursorLimitExceeded`] if max batches reached before completion
     /// - [`MegaEthError::Timeout`] if request times out
+    /// - The range or cursor error, once the range cannot be narrowed or the
+    ///   query restarted any further
     ///
     /// # Example
     ///
@@ -248,37 +298,44 @@ impl MegaEthClient {
         to_block: u64,
         addresses: Option<Vec<Address>>,
     ) -> Result<(Vec<Log>, FetchStats)> {
-        let mut filter = LogsWithCursorFilter::new(from_block, to_block);
-        if let Some(addrs) = addresses {
-            filter = filter.with_addresses(addrs);
-        }
+        let mut range = RangeFetch::new(from_block, to_block, addresses);
+        let mut filter = range.filter();
 
         let mut all_logs = Vec::new();
-        let mut batches = 0usize;
+        let mut stats = FetchStats::default();
 
         loop {
-            batches += 1;
+            stats.batches += 1;
 
-            if batches > self.config.max_cursor_batches {
+            if stats.batches > self.config.max_cursor_batches {
                 let total_logs = all_logs.len();
                 warn!(
-                    batches,
+                    batches = stats.batches,
                     total_logs,
                     max = self.config.max_cursor_batches,
                     "Reached max cursor batches, stopping"
                 );
                 return Err(MegaEthError::CursorLimitExceeded {
-                    batches,
+                    batches: stats.batches,
                     max: self.config.max_cursor_batches,
                 });
             }
 
-            debug!(batch = batches, cursor = ?filter.cursor, "Fetching logs batch");
+            debug!(batch = stats.batches, cursor = ?filter.cursor, "Fetching logs batch");
 
-            let response = self.get_logs_single_batch(&filter).await?;
+            let exchange = match self.get_logs_single_batch(&filter).await {
+                Ok(exchange) => exchange,
+                Err(e) => {
+                    filter = range.recover(e, &mut all_logs, &mut stats)?;
+                    continue;
+                }
+            };
+            stats.request_bytes += exchange.request_bytes;
+            stats.response_bytes += exchange.response_bytes;
+            let response = exchange.result;
 
             debug!(
-                batch = batches,
+                batch = stats.batches,
                 logs_in_batch = response.logs.len(),
                 has_cursor = response.cursor.is_some(),
                 "Batch received"
@@ -292,7 +349,7 @@ impl MegaEthClient {
                 warn!(
                     collected,
                     max = self.config.max_logs,
-                    batches,
+                    batches = stats.batches,
                     "Reached max logs limit, stopping"
                 );
                 return Err(MegaEthError::LogLimitExceeded {
@@ -303,18 +360,19 @@ impl MegaEthClient {
 
             if let Some(cursor) = response.cursor {
                 filter = filter.with_cursor(cursor);
+            } else if let Some(next) = range.next(all_logs.len()) {
+                // This range is done, the next one starts without a cursor
+                filter = next;
             } else {
                 // No more cursors - query complete
-                let total_logs = all_logs.len();
-                info!(total_logs, batches, "Cursor pagination complete");
-                return Ok((
-                    all_logs,
-                    FetchStats {
-                        total_logs,
-                        batches,
-                        complete: true,
-                    },
-                ));
+                stats.total_logs = all_logs.len();
+                info!(
+                    total_logs = stats.total_logs,
+                    batches = stats.batches,
+                    response_bytes = stats.response_bytes,
+                    "Cursor pagination complete"
+                );
+                return Ok((all_logs, stats));
             }
         }
     }
@@ -349,34 +407,29 @@ impl MegaEthClient {
     ///
     /// This is the low-level method that makes a single RPC call. For automatic
     /// pagination, use [`get_logs_with_cursor`](Self::get_logs_with_cursor).
-    async fn get_logs_single_batch(&self, filter: &LogsWithCursorFilter) -> Result<LogsWithCursorResponse> {
-        let request_id = self.next_request_id();
-        let request = JsonRpcRequest::new("eth_getLogsWithCursor", [filter], request_id);
-
-        let response: JsonRpcResponse<serde_json::Value> = self.send_request(&request).await?;
-
-        // Check for error
-        if let Some(error) = response.error {
-            return Err(error.into_error("eth_getLogsWithCursor"));
-        }
-
-        let result = response
-            .result
-            .ok_or_else(|| MegaEthError::InvalidResponse("Missing result in RPC response".into()))?;
+    async fn get_logs_single_batch(
+        &self,
+        filter: &LogsWithCursorFilter,
+    ) -> Result<Exchange<LogsWithCursorResponse>> {
+        let exchange: Exchange<LogsResult> = self.call(GET_LOGS_WITH_CURSOR, [filter], true).await?;
 
         // Handle both response formats:
         // 1. {logs: [...], cursor: "..."} - paginated format
         // 2. [...] - standard array format (fallback)
-        if result.is_array() {
-            // Standard eth_getLogs response format (no cursor support)
-            warn!("Endpoint returned standard eth_getLogs format (no cursor). This endpoint may not fully support eth_getLogsWithCursor.");
-            let logs: Vec<Log> = serde_json::from_value(result)?;
-            Ok(LogsWithCursorResponse { logs, cursor: None })
-        } else {
-            // Paginated format with cursor
-            let parsed: LogsWithCursorResponse = serde_json::from_value(result)?;
-            Ok(parsed)
-        }
+        let result = match exchange.result {
+            LogsResult::Paginated(parsed) => parsed,
+            LogsResult::Standard(logs) => {
+                // Standard eth_getLogs response format (no cursor support)
+                warn!("Endpoint returned standard eth_getLogs format (no cursor). This endpoint may not fully support eth_getLogsWithCursor.");
+                LogsWithCursorResponse { logs, cursor: None }
+            }
+        };
+
+        Ok(Exchange {
+            result,
+            request_bytes: exchange.request_bytes,
+            response_bytes: exchange.response_bytes,
+        })
     }
 
     // ───────────────────────────────────────────────────────────────────────────
@@ -394,11 +447,19 @@ impl MegaEthClient {
         // try a method probe. Send empty bytes which should fail with an
         // error different from "method not found" if supported.
         let request_id = self.next_request_id();
-        let request = JsonRpcRequest::new("realtime_sendRawTransaction", ["0x"], request_id);
+        let request = JsonRpcRequest::new(REALTIME_SEND_RAW_TRANSACTION, ["0x"], request_id);
 
+        let _permit = match self.limiter.acquire(REALTIME_SEND_RAW_TRANSACTION).await {
+            Ok(permit) => permit,
+            Err(e) => {
+                warn!(error = %e, "Failed to check realtime API support");
+                return false;
+            }
+        };
         match self
             .client
             .post(&self.rpc_url)
+            .timeout(self.config.timeout_for(REALTIME_SEND_RAW_TRANSACTION))
             .json(&request)
             .send()
             .await
@@ -461,43 +522,297 @@ impl MegaEthClient {
     /// ```
     #[instrument(skip(self, raw_tx), fields(tx_len = raw_tx.len()))]
     pub async fn send_realtime_transaction(&self, raw_tx: Bytes) -> Result<RealtimeResponse> {
-        let request_id = self.next_request_id();
         let hex_tx = format!("0x{}", hex::encode(raw_tx.as_ref()));
-        let request = JsonRpcRequest::new("realtime_sendRawTransaction", [&hex_tx], request_id);
-
-        let response: JsonRpcResponse<RealtimeResponse> = self.send_request(&request).await?;
-
-        if let Some(error) = response.error {
-            return Err(error.into_error("realtime_sendRawTransaction"));
-        }
 
-        response
-            .result
-            .ok_or_else(|| MegaEthError::InvalidResponse("Missing result in realtime response".into()))
+        // Only retried on a full queue: a timed out submission may still have been executed
+        let exchange = self.call(REALTIME_SEND_RAW_TRANSACTION, [&hex_tx], false).await?;
+        Ok(exchange.result)
     }
 
     // ───────────────────────────────────────────────────────────────────────────
     // INTERNAL HELPERS
     // ───────────────────────────────────────────────────────────────────────────
 
-    /// Send a JSON-RPC request and parse the response.
-    async fn send_request<P, R>(&self, request: &JsonRpcRequest<'_, P>) -> Result<JsonRpcResponse<R>>
+    /// Call a JSON-RPC method and return its result.
+    ///
+    /// Idempotent calls are retried up to [`ClientConfig::max_retries`] times on
+    /// retryable errors. Other calls are only retried when the server turned
+    /// them away unexecuted, as with [`MegaEthError::RealtimeQueueFull`]. The
+    /// final error is wrapped in [`MegaEthError::Request`].
+    ///
+    /// Each attempt holds a permit of the limiter while it is in flight, but
+    /// not during the backoff before a retry.
+    async fn call<P, R>(&self, method: &'static str, params: P, idempotent: bool) -> Result<Exchange<R>>
     where
         P: serde::Serialize + Sync,
         R: serde::de::DeserializeOwned,
     {
-        let response = self
+        let started = Instant::now();
+        let max_attempts = self.config.max_retries.saturating_add(1);
+        let retryable = |e: &MegaEthError| {
+            e.is_retryable() && (idempotent || matches!(e, MegaEthError::RealtimeQueueFull { .. }))
+        };
+        let mut bytes = Exchange::default();
+        let mut attempt = 1;
+
+        loop {
+            let request = JsonRpcRequest::new(method, &params, self.next_request_id());
+            let sent = match self.limiter.acquire(method).await {
+                Ok(_permit) => self.send_request(&request, &mut bytes).await,
+                Err(e) => Err(e),
+            };
+
+            match sent {
+                Ok(result) => {
+                    return Ok(Exchange {
+                        result,
+                        request_bytes: bytes.request_bytes,
+                        response_bytes: bytes.response_bytes,
+                    });
+                }
+                Err(e) if attempt < max_attempts && retryable(&e) => {
+                    let delay = RETRY_BASE_DELAY.saturating_mul(2u32.saturating_pow(attempt - 1));
+                    warn!(method, attempt, error = %e, ?delay, "Request failed, retrying");
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                Err(e) => {
+                    return Err(MegaEthError::Request {
+                        method: method.to_string(),
+                        attempt,
+                        elapsed: started.elapsed(),
+                        source: Box::new(e),
+                    });
+                }
+            }
+        }
+    }
+
+    /// Send a single JSON-RPC request and extract its result.
+    ///
+    /// Adds the bytes sent and received to `bytes`, also when the request fails.
+    async fn send_request<P, R>(&self, request: &JsonRpcRequest<'_, P>, bytes: &mut Exchange<()>) -> Result<R>
+    where
+        P: serde::Serialize + Sync,
+        R: serde::de::DeserializeOwned,
+    {
+        let method = request.method;
+        let body = serde_json::to_vec(request)?;
+        bytes.request_bytes += body.len();
+
+        let mut response = self
             .client
             .post(&self.rpc_url)
-            .json(request)
+            .timeout(self.config.timeout_for(method))
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body)
             .send()
             .await?;
 
-        let body: JsonRpcResponse<R> = response.json().await?;
+        let status = response.status();
+        let body = self.read_body(method, &mut response, bytes).await?;
+
+        let response: JsonRpcResponse<R> = match serde_json::from_slice(&body) {
+            Ok(response) => response,
+            // Not a JSON-RPC response: report the HTTP status instead
+            Err(_) if !status.is_success() => return Err(MegaEthError::Http(status.to_string())),
+            Err(e) => return Err(e.into()),
+        };
+
+        if let Some(error) = response.error {
+            return Err(error.into_error(method));
+        }
+
+        response
+            .result
+            .ok_or_else(|| MegaEthError::InvalidResponse(format!("Missing result in {method} response")))
+    }
+
+    /// Read a response body, aborting once it exceeds
+    /// [`ClientConfig::max_response_bytes`].
+    async fn read_body(
+        &self,
+        method: &str,
+        response: &mut reqwest::Response,
+        bytes: &mut Exchange<()>,
+    ) -> Result<Vec<u8>> {
+        let limit = self.config.max_response_bytes;
+        let too_large = |received: usize| MegaEthError::ResponseTooLarge {
+            method: method.to_string(),
+            bytes: received,
+            limit,
+        };
+
+        // Fail fast if the server announces an oversized body
+        if let Some(length) = response.content_length() {
+            let length = usize::try_from(length).unwrap_or(usize::MAX);
+            if limit > 0 && length > limit {
+                warn!(method, length, limit, "Response too large, not reading body");
+                return Err(too_large(length));
+            }
+        }
+
+        let mut body = Vec::new();
+        while let Some(chunk) = response.chunk().await? {
+            bytes.response_bytes += chunk.len();
+            body.extend_from_slice(&chunk);
+
+            if limit > 0 && body.len() > limit {
+                warn!(method, received = body.len(), limit, "Response too large, aborting");
+                return Err(too_large(body.len()));
+            }
+        }
+
         Ok(body)
     }
 }
 
+/// Progress of [`MegaEthClient::get_logs_with_cursor`] through its block
+/// range, which it may have to fetch in narrower ranges than asked for.
+#[derive(Debug)]
+struct RangeFetch {
+    /// First block of the range being fetched.
+    start: u64,
+
+    /// Last block of the range being fetched.
+    end: u64,
+
+    /// Blocks per range, once a range was narrowed.
+    span: u64,
+
+    /// Last block to fetch.
+    to_block: u64,
+
+    /// Address filter of every range.
+    addresses: Option<Vec<Address>>,
+
+    /// Index of the first log of the range being fetched.
+    first_log: usize,
+}
+
+impl RangeFetch {
+    const fn new(from_block: u64, to_block: u64, addresses: Option<Vec<Address>>) -> Self {
+        Self {
+            start: from_block,
+            end: to_block,
+            span: to_block.saturating_sub(from_block).saturating_add(1),
+            to_block,
+            addresses,
+            first_log: 0,
+        }
+    }
+
+    /// Filter for the range being fetched, without a cursor.
+    fn filter(&self) -> LogsWithCursorFilter {
+        let filter = LogsWithCursorFilter::new(self.start, self.end);
+        match &self.addresses {
+            Some(addresses) => filter.with_addresses(addresses.clone()),
+            None => filter,
+        }
+    }
+
+    /// Move on to the next range once the current one is done and `logs`
+    /// logs were collected, unless it was the last.
+    fn next(&mut self, logs: usize) -> Option<LogsWithCursorFilter> {
+        if self.end >= self.to_block {
+            return None;
+        }
+        self.start = self.end + 1;
+        self.end = self.start.saturating_add(self.span - 1).min(self.to_block);
+        self.first_log = logs;
+        Some(self.filter())
+    }
+
+    /// Continue after `error`, narrowing the range or starting it over.
+    ///
+    /// # Errors
+    ///
+    /// Returns `error` if it is neither a range nor a cursor error, or the
+    /// range cannot be narrowed or restarted any further.
+    fn recover(
+        &mut self,
+        error: MegaEthError,
+        logs: &mut Vec<Log>,
+        stats: &mut FetchStats,
+    ) -> Result<LogsWithCursorFilter> {
+        let narrowable = stats.range_splits < MAX_RANGE_SPLITS && self.end > self.start;
+        let allowed = match error.inner() {
+            MegaEthError::BlockRangeTooLarge { max_range, .. } if narrowable => *max_range,
+            MegaEthError::ResourceLimitExceeded {
+                suggested_smaller_range,
+                ..
+            } if narrowable => suggested_smaller_range
+                .as_ref()
+                .map(|range| range.end().saturating_sub(*range.start()).saturating_add(1)),
+            MegaEthError::CursorExpired { .. } if stats.cursor_restarts < MAX_CURSOR_RESTARTS => {
+                stats.cursor_restarts += 1;
+                self.resume(logs);
+                warn!(start = self.start, end = self.end, "Cursor expired, restarting query");
+                return Ok(self.filter());
+            }
+            _ => return Err(error),
+        };
+
+        stats.range_splits += 1;
+        self.narrow(allowed, logs);
+        warn!(
+            start = self.start,
+            end = self.end,
+            error = %error,
+            "Block range rejected, narrowing"
+        );
+        Ok(self.filter())
+    }
+
+    /// Halve the range, or narrow it to `allowed` blocks if that is less.
+    fn narrow(&mut self, allowed: Option<u64>, logs: &mut Vec<Log>) {
+        let half = (self.end - self.start).div_ceil(2);
+        self.span = allowed.map_or(half, |allowed| allowed.min(half)).max(1);
+        self.end = self.start + self.span - 1;
+        // The whole range is fetched again, in narrower ranges
+        logs.truncate(self.first_log);
+    }
+
+    /// Start the range over from its last block with logs, which may not
+    /// have all of them, keeping the logs of the blocks before it.
+    fn resume(&mut self, logs: &mut Vec<Log>) {
+        let range_logs = &logs[self.first_log..];
+        let last = range_logs.iter().filter_map(|log| log.block_number).max();
+        let Some(last) = last.filter(|last| *last > self.start) else {
+            logs.truncate(self.first_log);
+            return;
+        };
+        let complete = range_logs.partition_point(|log| log.block_number.is_some_and(|block| block < last));
+        logs.truncate(self.first_log + complete);
+        self.start = last;
+        self.first_log = logs.len();
+    }
+}
+
+/// Result of a JSON-RPC call and the bytes it transferred.
+#[derive(Debug, Default)]
+struct Exchange<R> {
+    /// The call's result.
+    result: R,
+
+    /// Bytes sent in request bodies, across all attempts.
+    request_bytes: usize,
+
+    /// Bytes received in response bodies, across all attempts.
+    response_bytes: usize,
+}
+
+/// Result of `eth_getLogsWithCursor` in either of the formats endpoints return.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum LogsResult {
+    /// `{logs: [...], cursor: "..."}` - paginated format.
+    Paginated(LogsWithCursorResponse),
+
+    /// `[...]` - standard `eth_getLogs` format.
+    Standard(Vec<Log>),
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // TESTS
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -507,6 +822,7 @@ mod tests {
     use std::time::Duration;
 
     use super::*;
+    use crate::config::{DEFAULT_LOG_QUERIES_IN_FLIGHT, DEFAULT_MAX_IN_FLIGHT};
     use wiremock::matchers::{body_partial_json, method, path};
     use wiremock::{Mock, MockServer, ResponseTemplate};
 
@@ -591,6 +907,8 @@ mod tests {
         assert!(logs.is_empty());
         assert_eq!(stats.batches, 1);
         assert!(stats.complete);
+        assert!(stats.request_bytes > 0);
+        assert!(stats.response_bytes > 0);
     }
 
     #[tokio::test]
@@ -720,4 +1038,461 @@ mod tests {
         assert!(logs.is_empty());
         assert!(stats.complete);
     }
+
+    #[tokio::test]
+    async fn method_timeout_beats_default() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_delay(Duration::from_millis(1500))
+                    .set_body_json(serde_json::json!({
+                        "jsonrpc": "2.0",
+                        "id": 1,
+                        "result": {"logs": [], "cursor": null}
+                    })),
+            )
+            .mount(&mock_server)
+            .await;
+
+        // The default alone would time out before the response arrives
+        let config = ClientConfig::default()
+            .with_timeout(Duration::from_secs(1))
+            .with_method_timeout("eth_getLogsWithCursor", Duration::from_secs(10));
+        let client = MegaEthClient::with_config(mock_server.uri(), config).expect("client creation failed");
+
+        let (_, stats) = client
+            .get_logs_with_cursor(100, 200, None)
+            .await
+            .expect("method timeout should apply");
+        assert!(stats.complete);
+
+        let client = MegaEthClient::with_config(
+            mock_server.uri(),
+            ClientConfig::default().with_timeout(Duration::from_secs(1)),
+        )
+        .expect("client creation failed");
+
+        let error = client
+            .get_logs_with_cursor(100, 200, None)
+            .await
+            .expect_err("default timeout should apply");
+        assert!(matches!(error.inner(), MegaEthError::Timeout));
+        assert!(error.to_string().starts_with("eth_getLogsWithCursor failed on attempt 1 after"));
+    }
+
+    #[tokio::test]
+    async fn retries_are_counted_in_error() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(503).set_body_string("Service Unavailable"))
+            .expect(3)
+            .mount(&mock_server)
+            .await;
+
+        let config = ClientConfig::default().with_max_retries(2);
+        let client = MegaEthClient::with_config(mock_server.uri(), config).expect("client creation failed");
+
+        let error = client
+            .get_logs_with_cursor(100, 200, None)
+            .await
+            .expect_err("every attempt fails");
+
+        assert!(matches!(error, MegaEthError::Request { attempt: 3, .. }));
+        assert!(matches!(error.inner(), MegaEthError::Http(status) if status.contains("503")));
+    }
+
+    /// Serve one HTTP request with a chunked body of `chunks` x `chunk_size` spaces,
+    /// which never announces its length up front.
+    async fn serve_chunked_body(chunks: usize, chunk_size: usize) -> String {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.expect("bind failed");
+        let addr = listener.local_addr().expect("no local address");
+
+        tokio::spawn(async move {
+            let Ok((mut stream, _)) = listener.accept().await else {
+                return;
+            };
+            let mut request = vec![0; 8192];
+            let _ = stream.read(&mut request).await;
+
+            let head = "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ntransfer-encoding: chunked\r\n\r\n";
+            if stream.write_all(head.as_bytes()).await.is_err() {
+                return;
+            }
+            let mut chunk = format!("{chunk_size:x}\r\n").into_bytes();
+            chunk.extend(std::iter::repeat_n(b' ', chunk_size));
+            chunk.extend_from_slice(b"\r\n");
+            for _ in 0..chunks {
+                // The client hangs up once it has seen enough
+                if stream.write_all(&chunk).await.is_err() {
+                    return;
+                }
+            }
+            let _ = stream.write_all(b"0\r\n\r\n").await;
+        });
+
+        format!("http://{addr}")
+    }
+
+    #[tokio::test]
+    async fn oversized_response_is_aborted() {
+        const LIMIT: usize = 64 * 1024;
+        const CHUNK: usize = 8 * 1024;
+        const CHUNKS: usize = 1024;
+
+        let url = serve_chunked_body(CHUNKS, CHUNK).await;
+        let config = ClientConfig::default().with_max_response_bytes(LIMIT);
+        let client = MegaEthClient::with_config(url, config).expect("client creation failed");
+
+        let error = client
+            .get_logs_with_cursor(100, 200, None)
+            .await
+            .expect_err("response exceeds the limit");
+
+        let MegaEthError::ResponseTooLarge { method, bytes, limit } = error.inner() else {
+            unreachable!("expected ResponseTooLarge, got {error:?}");
+        };
+        assert_eq!(method, "eth_getLogsWithCursor");
+        assert_eq!(*limit, LIMIT);
+        assert!(*bytes > LIMIT);
+        // Aborted while streaming, not after buffering everything
+        assert!(*bytes < CHUNK * CHUNKS);
+        assert!(!error.is_retryable());
+    }
+
+    #[tokio::test]
+    async fn announced_oversized_response_is_rejected() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(" ".repeat(2048)))
+            .mount(&mock_server)
+            .await;
+
+        let config = ClientConfig::default().with_max_response_bytes(1024);
+        let client = MegaEthClient::with_config(mock_server.uri(), config).expect("client creation failed");
+
+        let error = client
+            .get_logs_with_cursor(100, 200, None)
+            .await
+            .expect_err("response exceeds the limit");
+        assert!(matches!(
+            error.inner(),
+            MegaEthError::ResponseTooLarge { bytes: 2048, limit: 1024, .. }
+        ));
+    }
+
+    /// A log of `block`, as `eth_getLogsWithCursor` returns it.
+    fn log_json(block: u64) -> serde_json::Value {
+        serde_json::json!({
+            "address": "0x1234567890123456789012345678901234567890",
+            "topics": [],
+            "data": "0x",
+            "blockNumber": format!("0x{block:x}"),
+            "transactionHash": format!("0x{block:064x}"),
+            "transactionIndex": "0x0",
+            "blockHash": "0x0000000000000000000000000000000000000000000000000000000000000000",
+            "logIndex": "0x0",
+            "removed": false
+        })
+    }
+
+    /// Server with one log per block, rejecting ranges wider than `max_span`
+    /// blocks with `error`, and recording the ranges it was asked for.
+    struct RangeLimitedServer {
+        max_span: u64,
+        error: serde_json::Value,
+        ranges: std::sync::Arc<std::sync::Mutex<Vec<(u64, u64)>>>,
+    }
+
+    impl RangeLimitedServer {
+        fn new(max_span: u64, error: serde_json::Value) -> Self {
+            Self {
+                max_span,
+                error,
+                ranges: std::sync::Arc::default(),
+            }
+        }
+    }
+
+    impl wiremock::Respond for RangeLimitedServer {
+        fn respond(&self, request: &wiremock::Request) -> ResponseTemplate {
+            let body: serde_json::Value = serde_json::from_slice(&request.body).expect("request is JSON");
+            let block = |key: &str| {
+                let hex = body["params"][0][key].as_str().expect("block is hex");
+                u64::from_str_radix(hex.trim_start_matches("0x"), 16).expect("block is hex")
+            };
+            let (from, to) = (block("fromBlock"), block("toBlock"));
+            self.ranges.lock().expect("lock poisoned").push((from, to));
+
+            if to - from + 1 > self.max_span {
+                return ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"jsonrpc": "2.0", "id": 1, "error": self.error}));
+            }
+            let logs: Vec<_> = (from..=to).map(log_json).collect();
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "result": {"logs": logs, "cursor": null}
+            }))
+        }
+    }
+
+    /// Fetch blocks 100 to 199 from a range-limited server.
+    async fn fetch_limited(server: RangeLimitedServer) -> (Result<(Vec<Log>, FetchStats)>, Vec<(u64, u64)>) {
+        let mock_server = MockServer::start().await;
+        let ranges = std::sync::Arc::clone(&server.ranges);
+        Mock::given(method("POST")).respond_with(server).mount(&mock_server).await;
+
+        let client = MegaEthClient::new(mock_server.uri()).expect("client creation failed");
+        let result = client.get_logs_with_cursor(100, 199, None).await;
+        let ranges = ranges.lock().expect("lock poisoned").clone();
+        (result, ranges)
+    }
+
+    fn blocks(logs: &[Log]) -> Vec<u64> {
+        logs.iter().filter_map(|log| log.block_number).collect()
+    }
+
+    #[tokio::test]
+    async fn rejected_ranges_are_halved() {
+        let error = serde_json::json!({"code": -32005, "message": "block range too large"});
+        let (result, ranges) = fetch_limited(RangeLimitedServer::new(30, error)).await;
+        let (logs, stats) = result.expect("fetch failed");
+
+        // 100 and 50 blocks are rejected, the rest is fetched 25 at a time
+        assert_eq!(
+            ranges,
+            [(100, 199), (100, 149), (100, 124), (125, 149), (150, 174), (175, 199)]
+        );
+        assert_eq!(blocks(&logs), (100..=199).collect::<Vec<_>>());
+        assert_eq!((stats.total_logs, stats.batches, stats.range_splits), (100, 6, 2));
+    }
+
+    #[tokio::test]
+    async fn suggested_ranges_are_followed() {
+        let error = serde_json::json!({
+            "code": -32005,
+            "message": "query exceeds resource limits",
+            "data": {"suggestedRange": {"fromBlock": "0x64", "toBlock": "0x8b"}}
+        });
+        let (result, ranges) = fetch_limited(RangeLimitedServer::new(40, error)).await;
+        let (logs, stats) = result.expect("fetch failed");
+
+        // The server suggested 40 blocks, less than half the range
+        assert_eq!(ranges, [(100, 199), (100, 139), (140, 179), (180, 199)]);
+        assert_eq!(blocks(&logs), (100..=199).collect::<Vec<_>>());
+        assert_eq!(stats.range_splits, 1);
+    }
+
+    #[tokio::test]
+    async fn range_halving_is_bounded() {
+        let error = serde_json::json!({
+            "code": -32005,
+            "message": "block range too large",
+            "data": {"maxRange": 0}
+        });
+        let (result, ranges) = fetch_limited(RangeLimitedServer::new(0, error)).await;
+
+        // A single block cannot be narrowed any further
+        let error = result.expect_err("every range is rejected");
+        assert!(matches!(error.inner(), MegaEthError::BlockRangeTooLarge { max_range: Some(0), .. }));
+        assert_eq!(ranges, [(100, 199), (100, 100)]);
+    }
+
+    #[tokio::test]
+    async fn expired_cursors_restart_from_the_last_complete_block() {
+        use std::sync::atomic::{AtomicU32, Ordering};
+
+        // Blocks 100 and 101 arrive, block 101 maybe in part, then the cursor expires
+        let calls = std::sync::Arc::new(AtomicU32::new(0));
+        let responder = {
+            let calls = std::sync::Arc::clone(&calls);
+            move |request: &wiremock::Request| {
+                let body: serde_json::Value = serde_json::from_slice(&request.body).expect("request is JSON");
+                let params = &body["params"][0];
+                let expired = serde_json::json!({"code": -32602, "message": "cursor expired"});
+                let (result, error) = match calls.fetch_add(1, Ordering::SeqCst) {
+                    0 => (serde_json::json!({"logs": [log_json(100), log_json(101)], "cursor": "c1"}), None),
+                    1 => (serde_json::Value::Null, Some(expired)),
+                    _ => {
+                        assert_eq!(params["fromBlock"], "0x65");
+                        assert!(params.get("cursor").is_none());
+                        (serde_json::json!({"logs": [log_json(101), log_json(102)], "cursor": null}), None)
+                    }
+                };
+                let body = error.map_or_else(
+                    || serde_json::json!({"jsonrpc": "2.0", "id": 1, "result": result}),
+                    |error| serde_json::json!({"jsonrpc": "2.0", "id": 1, "error": error}),
+                );
+                ResponseTemplate::new(200).set_body_json(body)
+            }
+        };
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST")).respond_with(responder).mount(&mock_server).await;
+        let client = MegaEthClient::new(mock_server.uri()).expect("client creation failed");
+        let (logs, stats) = client.get_logs_with_cursor(100, 102, None).await.expect("fetch failed");
+
+        assert_eq!(blocks(&logs), [100, 101, 102]);
+        assert_eq!((stats.batches, stats.cursor_restarts), (3, 1));
+    }
+
+    #[tokio::test]
+    async fn full_realtime_queue_is_retried() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "error": {"code": -32000, "message": "realtime queue full"}
+            })))
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+
+        let config = ClientConfig::default().with_max_retries(1);
+        let client = MegaEthClient::with_config(mock_server.uri(), config).expect("client creation failed");
+        let error = client
+            .send_realtime_transaction(Bytes::from_static(&[1, 2, 3]))
+            .await
+            .expect_err("the queue stays full");
+
+        assert!(matches!(error, MegaEthError::Request { attempt: 2, .. }));
+        assert!(matches!(error.inner(), MegaEthError::RealtimeQueueFull { .. }));
+    }
+
+    /// Requests a [`slow_server`] is serving, and the most it served at once.
+    #[derive(Debug, Default)]
+    struct Concurrency {
+        serving: std::sync::atomic::AtomicUsize,
+        peak: std::sync::atomic::AtomicUsize,
+        log_queries: std::sync::atomic::AtomicUsize,
+        log_query_peak: std::sync::atomic::AtomicUsize,
+    }
+
+    /// Serve every request after `delay`, one per connection: an empty page
+    /// of logs for log queries, `null` otherwise.
+    async fn slow_server(delay: Duration) -> (String, std::sync::Arc<Concurrency>) {
+        use std::sync::atomic::Ordering::SeqCst;
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.expect("bind failed");
+        let addr = listener.local_addr().expect("no local address");
+        let concurrency = std::sync::Arc::new(Concurrency::default());
+        let counts = std::sync::Arc::clone(&concurrency);
+
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let counts = std::sync::Arc::clone(&counts);
+                tokio::spawn(async move {
+                    // Read the head, then as much body as it announces
+                    let mut request = Vec::new();
+                    let mut buf = [0; 4096];
+                    loop {
+                        let Ok(read @ 1..) = stream.read(&mut buf).await else {
+                            return;
+                        };
+                        request.extend_from_slice(&buf[..read]);
+                        let text = String::from_utf8_lossy(&request).to_ascii_lowercase();
+                        let Some(end) = text.find("\r\n\r\n") else {
+                            continue;
+                        };
+                        let length = text[..end]
+                            .lines()
+                            .find_map(|line| line.strip_prefix("content-length:"))
+                            .and_then(|length| length.trim().parse::<usize>().ok())
+                            .unwrap_or(0);
+                        if request.len() >= end + 4 + length {
+                            break;
+                        }
+                    }
+                    let log_query = String::from_utf8_lossy(&request).contains(GET_LOGS_WITH_CURSOR);
+
+                    let serving = counts.serving.fetch_add(1, SeqCst) + 1;
+                    counts.peak.fetch_max(serving, SeqCst);
+                    if log_query {
+                        let serving = counts.log_queries.fetch_add(1, SeqCst) + 1;
+                        counts.log_query_peak.fetch_max(serving, SeqCst);
+                    }
+                    tokio::time::sleep(delay).await;
+                    counts.serving.fetch_sub(1, SeqCst);
+                    if log_query {
+                        counts.log_queries.fetch_sub(1, SeqCst);
+                    }
+
+                    let result = if log_query { r#"{"logs":[],"cursor":null}"# } else { "null" };
+                    let body = format!(r#"{{"jsonrpc":"2.0","id":1,"result":{result}}}"#);
+                    let response = format!(
+                        "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                        body.len()
+                    );
+                    let _ = stream.write_all(response.as_bytes()).await;
+                });
+            }
+        });
+
+        (format!("http://{addr}"), concurrency)
+    }
+
+    #[tokio::test]
+    async fn in_flight_caps_are_never_exceeded() {
+        use std::sync::atomic::Ordering::SeqCst;
+
+        let (url, concurrency) = slow_server(Duration::from_millis(20)).await;
+        let config = ClientConfig::default().with_max_queued(100);
+        let client = MegaEthClient::with_config(url, config).expect("client creation failed");
+
+        // 60 log queries and 40 cheap calls at once
+        let log_queries = futures::future::join_all((0..60).map(|_| client.get_logs_with_cursor(100, 200, None)));
+        let realtime_checks = futures::future::join_all((0..40).map(|_| client.supports_realtime_api()));
+        let (fetched, realtime) = tokio::join!(log_queries, realtime_checks);
+
+        assert!(fetched.iter().all(Result::is_ok));
+        assert!(realtime.into_iter().all(|supported| supported));
+        assert!(concurrency.peak.load(SeqCst) <= DEFAULT_MAX_IN_FLIGHT);
+        assert!(concurrency.log_query_peak.load(SeqCst) <= DEFAULT_LOG_QUERIES_IN_FLIGHT);
+        // Log queries never took the permits the cheap calls needed
+        assert!(concurrency.peak.load(SeqCst) > DEFAULT_LOG_QUERIES_IN_FLIGHT);
+
+        let stats = client.stats();
+        assert_eq!((stats.in_flight, stats.queued), (0, 0));
+        assert!(stats.in_flight_high_water <= DEFAULT_MAX_IN_FLIGHT);
+        assert!(stats.in_flight_high_water >= concurrency.peak.load(SeqCst));
+        assert_eq!((stats.requests, stats.shed), (100, 0));
+        assert_eq!(stats.methods[GET_LOGS_WITH_CURSOR].requests, 60);
+        assert_eq!(stats.methods[REALTIME_SEND_RAW_TRANSACTION].requests, 40);
+    }
+
+    #[tokio::test]
+    async fn full_queue_sheds_load() {
+        let (url, concurrency) = slow_server(Duration::from_millis(100)).await;
+        let config = ClientConfig::default().with_max_in_flight(1).with_max_queued(2);
+        let client = MegaEthClient::with_config(url, config).expect("client creation failed");
+
+        // One request in flight and two queued, the rest is shed right away
+        let results = futures::future::join_all((0..10).map(|_| client.get_logs_with_cursor(100, 200, None))).await;
+
+        let (fetched, shed): (Vec<_>, Vec<_>) = results.into_iter().partition(Result::is_ok);
+        assert_eq!(fetched.len(), 3);
+        for error in shed.into_iter().filter_map(Result::err) {
+            assert!(matches!(
+                error.inner(),
+                MegaEthError::ClientOverloaded { method, max_queued: 2 } if method == GET_LOGS_WITH_CURSOR
+            ));
+            assert!(!error.is_retryable());
+        }
+        assert_eq!(concurrency.peak.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        // The last request waited for the two before it
+        let stats = client.stats();
+        assert_eq!((stats.requests, stats.shed, stats.in_flight_high_water), (3, 7, 1));
+        assert_eq!(stats.methods[GET_LOGS_WITH_CURSOR].shed, 7);
+        assert!(stats.queue_wait_p95 >= Duration::from_millis(150), "{:?}", stats.queue_wait_p95);
+        assert!(stats.queue_wait_p95 < Duration::from_secs(5));
+    }
 }
@@ -2,9 +2,18 @@
 //!
 //! This module provides [`ClientConfig`] for customizing client behavior:
 //!
-//! - Request timeouts
+//! - Request timeouts, with per-method overrides
 //! - Cursor pagination limits
-//! - Future: retry policies, connection pooling
+//! - Response size limits
+//! - Retries of idempotent requests
+//! - Concurrent requests, with per-method caps and a bounded queue
+//!
+//! and [`WsConfig`] for the WebSocket subscriptions of
+//! [`MegaEthWsClient`](crate::MegaEthWsClient):
+//!
+//! - Keep-alive interval
+//! - Connection and subscription timeouts
+//! - Reconnect behavior via [`ReconnectPolicy`]
 //!
 //! # Example
 //!
@@ -17,6 +26,7 @@
 //!     .with_max_cursor_batches(200);
 //! ```
 
+use std::collections::HashMap;
 use std::time::Duration;
 
 use crate::error::{MegaEthError, Result};
@@ -50,6 +60,50 @@ pub const MIN_CURSOR_BATCHES: usize = 1;
 /// Maximum allowed cursor batches.
 pub const MAX_CURSOR_BATCHES: usize = 10_000;
 
+/// Default maximum size of a single response body (128 MiB).
+pub const DEFAULT_MAX_RESPONSE_BYTES: usize = 128 * 1024 * 1024;
+
+/// Default number of retries for idempotent requests.
+pub const DEFAULT_MAX_RETRIES: u32 = 0;
+
+/// Maximum allowed retries.
+pub const MAX_RETRIES: u32 = 10;
+
+/// Delay before the first retry; doubled for every further retry.
+pub const RETRY_BASE_DELAY: Duration = Duration::from_millis(250);
+
+/// Default maximum requests in flight at once.
+pub const DEFAULT_MAX_IN_FLIGHT: usize = 8;
+
+/// Maximum allowed requests in flight.
+pub const MAX_IN_FLIGHT: usize = 1024;
+
+/// Default maximum `eth_getLogsWithCursor` requests in flight at once.
+pub const DEFAULT_LOG_QUERIES_IN_FLIGHT: usize = 2;
+
+/// Default maximum requests waiting for a permit.
+pub const DEFAULT_MAX_QUEUED: usize = 64;
+
+/// Maximum allowed queued requests.
+pub const MAX_QUEUED: usize = 100_000;
+
+/// Default interval between WebSocket keep-alive requests.
+///
+/// MegaETH closes WebSocket connections without activity for 30 seconds.
+pub const DEFAULT_KEEPALIVE_INTERVAL: Duration = Duration::from_secs(25);
+
+/// Default timeout for establishing a WebSocket connection.
+pub const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Default timeout for the server to confirm a subscription.
+pub const DEFAULT_SUBSCRIBE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Default delay between WebSocket reconnection attempts.
+pub const DEFAULT_RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+/// Default maximum consecutive WebSocket reconnection attempts.
+pub const DEFAULT_MAX_RECONNECT_ATTEMPTS: u32 = 10;
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // CLIENT CONFIG
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -65,7 +119,8 @@ pub const MAX_CURSOR_BATCHES: usize = 10_000;
 /// let config = ClientConfig::default()
 ///     .with_timeout(Duration::from_secs(60))
 ///     .with_max_cursor_batches(200)
-///     .with_max_logs(100_000);
+///     .with_max_logs(100_000)
+///     .with_method_timeout("eth_getLogsWithCursor", Duration::from_secs(60));
 /// ```
 ///
 /// # Memory Considerations
@@ -100,6 +155,57 @@ pub struct ClientConfig {
     /// Set to 0 (default) for unlimited - only `max_cursor_batches` applies.
     /// Range: 0-10,000,000 logs.
     pub max_logs: usize,
+
+    /// Timeout overrides per JSON-RPC method name.
+    ///
+    /// A method listed here uses its own timeout instead of [`timeout`](Self::timeout).
+    /// Range: 1-300 seconds each.
+    pub method_timeouts: HashMap<String, Duration>,
+
+    /// Maximum size of a single response body in bytes.
+    ///
+    /// Larger responses are aborted while streaming and fail with
+    /// [`MegaEthError::ResponseTooLarge`] instead of being buffered.
+    ///
+    /// Default: 128 MiB. Set to 0 for unlimited.
+    pub max_response_bytes: usize,
+
+    /// Number of times a failed idempotent request is retried.
+    ///
+    /// Only retryable errors (see [`MegaEthError::is_retryable`]) are retried,
+    /// and never for `realtime_sendRawTransaction`. Retries back off
+    /// exponentially starting at 250ms.
+    ///
+    /// Default: 0 (no retries).
+    /// Range: 0-10.
+    pub max_retries: u32,
+
+    /// Maximum number of requests in flight at once.
+    ///
+    /// Further requests wait for one of them to finish, see
+    /// [`max_queued`](Self::max_queued).
+    ///
+    /// Default: 8.
+    /// Range: 1-1,024.
+    pub max_in_flight: usize,
+
+    /// Caps per JSON-RPC method name, within [`max_in_flight`](Self::max_in_flight).
+    ///
+    /// A method listed here never holds more than its cap of the shared
+    /// permits, leaving the rest to other methods.
+    ///
+    /// Default: 2 for `eth_getLogsWithCursor`.
+    /// Range: at least 1 each.
+    pub method_in_flight: HashMap<String, usize>,
+
+    /// Maximum number of requests waiting for a permit.
+    ///
+    /// Requests beyond it fail with [`MegaEthError::ClientOverloaded`]
+    /// instead of queuing.
+    ///
+    /// Default: 64. Set to 0 to never wait.
+    /// Range: 0-100,000.
+    pub max_queued: usize,
 }
 
 impl Default for ClientConfig {
@@ -108,6 +214,15 @@ impl Default for ClientConfig {
             timeout: DEFAULT_REQUEST_TIMEOUT,
             max_cursor_batches: DEFAULT_MAX_CURSOR_BATCHES,
             max_logs: DEFAULT_MAX_LOGS,
+            method_timeouts: HashMap::new(),
+            max_response_bytes: DEFAULT_MAX_RESPONSE_BYTES,
+            max_retries: DEFAULT_MAX_RETRIES,
+            max_in_flight: DEFAULT_MAX_IN_FLIGHT,
+            method_in_flight: HashMap::from([(
+                "eth_getLogsWithCursor".to_string(),
+                DEFAULT_LOG_QUERIES_IN_FLIGHT,
+            )]),
+            max_queued: DEFAULT_MAX_QUEUED,
         }
     }
 }
@@ -168,6 +283,99 @@ impl ClientConfig {
         self
     }
 
+    /// Override the request timeout for one JSON-RPC method.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use megaeth_rpc::ClientConfig;
+    /// use std::time::Duration;
+    ///
+    /// // Large cursor queries may take a while, everything else fails fast
+    /// let config = ClientConfig::default()
+    ///     .with_timeout(Duration::from_secs(2))
+    ///     .with_method_timeout("eth_getLogsWithCursor", Duration::from_secs(60));
+    ///
+    /// assert_eq!(config.timeout_for("eth_getLogsWithCursor"), Duration::from_secs(60));
+    /// assert_eq!(config.timeout_for("eth_blockNumber"), Duration::from_secs(2));
+    /// ```
+    #[must_use]
+    pub fn with_method_timeout(mut self, method: impl Into<String>, timeout: Duration) -> Self {
+        self.method_timeouts.insert(method.into(), timeout);
+        self
+    }
+
+    /// Set the maximum size of a single response body.
+    ///
+    /// # Arguments
+    ///
+    /// * `max` - Maximum size in bytes (0 for unlimited)
+    #[must_use]
+    pub const fn with_max_response_bytes(mut self, max: usize) -> Self {
+        self.max_response_bytes = max;
+        self
+    }
+
+    /// Set the number of retries for idempotent requests.
+    ///
+    /// # Arguments
+    ///
+    /// * `max` - Maximum number of retries (0-10)
+    #[must_use]
+    pub const fn with_max_retries(mut self, max: u32) -> Self {
+        self.max_retries = max;
+        self
+    }
+
+    /// Set the maximum number of requests in flight at once.
+    ///
+    /// # Arguments
+    ///
+    /// * `max` - Maximum number of requests (1-1,024)
+    #[must_use]
+    pub const fn with_max_in_flight(mut self, max: usize) -> Self {
+        self.max_in_flight = max;
+        self
+    }
+
+    /// Cap the requests of one JSON-RPC method in flight at once.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use megaeth_rpc::ClientConfig;
+    ///
+    /// // Log queries take at most 4 of the 16 permits
+    /// let config = ClientConfig::default()
+    ///     .with_max_in_flight(16)
+    ///     .with_method_in_flight("eth_getLogsWithCursor", 4);
+    /// ```
+    #[must_use]
+    pub fn with_method_in_flight(mut self, method: impl Into<String>, max: usize) -> Self {
+        self.method_in_flight.insert(method.into(), max);
+        self
+    }
+
+    /// Set the maximum number of requests waiting for a permit.
+    ///
+    /// # Arguments
+    ///
+    /// * `max` - Maximum number of queued requests (0 to never wait, max 100,000)
+    #[must_use]
+    pub const fn with_max_queued(mut self, max: usize) -> Self {
+        self.max_queued = max;
+        self
+    }
+
+    /// Timeout for a JSON-RPC method: its override if set, the default otherwise.
+    #[must_use]
+    pub fn timeout_for(&self, method: &str) -> Duration {
+        self.method_timeouts
+            .get(method)
+            .copied()
+            .unwrap_or(self.timeout)
+    }
+
     /// Validate the configuration.
     ///
     /// Called automatically when creating a client. Returns an error if
@@ -177,7 +385,11 @@ impl ClientConfig {
     ///
     /// Returns [`MegaEthError::InvalidConfig`] if:
     /// - Timeout is less than 1 second or greater than 300 seconds
+    /// - A method timeout is outside the same range
     /// - Max cursor batches is 0 or greater than 10,000
+    /// - Max retries is greater than 10
+    /// - Max in flight is 0 or greater than 1,024, or a method's cap is 0
+    /// - Max queued is greater than 100,000
     pub fn validate(&self) -> Result<()> {
         if self.timeout < MIN_TIMEOUT {
             return Err(MegaEthError::InvalidConfig(format!(
@@ -209,6 +421,184 @@ impl ClientConfig {
             )));
         }
 
+        for (method, timeout) in &self.method_timeouts {
+            if *timeout < MIN_TIMEOUT || *timeout > MAX_TIMEOUT {
+                return Err(MegaEthError::InvalidConfig(format!(
+                    "timeout for {method} must be between {MIN_TIMEOUT:?} and {MAX_TIMEOUT:?}"
+                )));
+            }
+        }
+
+        if self.max_retries > MAX_RETRIES {
+            return Err(MegaEthError::InvalidConfig(format!(
+                "max_retries must be at most {MAX_RETRIES}"
+            )));
+        }
+
+        if self.max_in_flight == 0 || self.max_in_flight > MAX_IN_FLIGHT {
+            return Err(MegaEthError::InvalidConfig(format!(
+                "max_in_flight must be between 1 and {MAX_IN_FLIGHT}"
+            )));
+        }
+
+        if let Some((method, _)) = self.method_in_flight.iter().find(|(_, max)| **max == 0) {
+            return Err(MegaEthError::InvalidConfig(format!(
+                "max in flight for {method} must be at least 1"
+            )));
+        }
+
+        if self.max_queued > MAX_QUEUED {
+            return Err(MegaEthError::InvalidConfig(format!(
+                "max_queued must be at most {MAX_QUEUED}"
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// WEBSOCKET CONFIG
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// What a subscription does when its WebSocket connection is lost.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReconnectPolicy {
+    /// Reconnect and re-subscribe automatically.
+    ///
+    /// Items produced while disconnected are missed. The subscription only
+    /// yields an error and ends once `max_attempts` consecutive attempts
+    /// have failed.
+    Auto {
+        /// Maximum consecutive reconnection attempts.
+        max_attempts: u32,
+        /// Delay before each attempt.
+        delay: Duration,
+    },
+
+    /// Yield the error and end the subscription.
+    ///
+    /// Use this when the caller has its own reconnect logic, e.g. to backfill
+    /// what was missed while disconnected.
+    Disabled,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self::Auto {
+            max_attempts: DEFAULT_MAX_RECONNECT_ATTEMPTS,
+            delay: DEFAULT_RECONNECT_DELAY,
+        }
+    }
+}
+
+/// Configuration options for [`MegaEthWsClient`](crate::MegaEthWsClient).
+///
+/// ```
+/// use megaeth_rpc::{ReconnectPolicy, WsConfig};
+/// use std::time::Duration;
+///
+/// let config = WsConfig::default()
+///     .with_keepalive_interval(Duration::from_secs(20))
+///     .with_reconnect(ReconnectPolicy::Disabled);
+/// ```
+#[derive(Debug, Clone)]
+pub struct WsConfig {
+    /// Interval between keep-alive requests (`eth_chainId`) on idle and busy
+    /// connections alike.
+    ///
+    /// Default: 25 seconds. MegaETH drops connections after 30 seconds
+    /// without activity.
+    pub keepalive_interval: Duration,
+
+    /// Timeout for establishing the WebSocket connection.
+    ///
+    /// Default: 10 seconds.
+    pub connect_timeout: Duration,
+
+    /// Timeout for the server to confirm an `eth_subscribe` request.
+    ///
+    /// Default: 10 seconds.
+    pub subscribe_timeout: Duration,
+
+    /// What to do when a connection is lost.
+    ///
+    /// Default: reconnect up to 10 times, 1 second apart.
+    pub reconnect: ReconnectPolicy,
+}
+
+impl Default for WsConfig {
+    fn default() -> Self {
+        Self {
+            keepalive_interval: DEFAULT_KEEPALIVE_INTERVAL,
+            connect_timeout: DEFAULT_CONNECT_TIMEOUT,
+            subscribe_timeout: DEFAULT_SUBSCRIBE_TIMEOUT,
+            reconnect: ReconnectPolicy::default(),
+        }
+    }
+}
+
+impl WsConfig {
+    /// Create a new configuration with default values.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the keep-alive interval.
+    #[must_use]
+    pub const fn with_keepalive_interval(mut self, interval: Duration) -> Self {
+        self.keepalive_interval = interval;
+        self
+    }
+
+    /// Set the connection timeout.
+    #[must_use]
+    pub const fn with_connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = timeout;
+        self
+    }
+
+    /// Set the subscription confirmation timeout.
+    #[must_use]
+    pub const fn with_subscribe_timeout(mut self, timeout: Duration) -> Self {
+        self.subscribe_timeout = timeout;
+        self
+    }
+
+    /// Set the reconnect policy.
+    #[must_use]
+    pub const fn with_reconnect(mut self, policy: ReconnectPolicy) -> Self {
+        self.reconnect = policy;
+        self
+    }
+
+    /// Validate the configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MegaEthError::InvalidConfig`] if:
+    /// - Any interval or timeout is zero
+    /// - Automatic reconnection allows zero attempts
+    pub fn validate(&self) -> Result<()> {
+        let durations = [
+            ("keepalive_interval", self.keepalive_interval),
+            ("connect_timeout", self.connect_timeout),
+            ("subscribe_timeout", self.subscribe_timeout),
+        ];
+        if let Some((name, _)) = durations.iter().find(|(_, value)| value.is_zero()) {
+            return Err(MegaEthError::InvalidConfig(format!(
+                "{name} must be greater than zero"
+            )));
+        }
+
+        if let ReconnectPolicy::Auto { max_attempts: 0, .. } = self.reconnect {
+            return Err(MegaEthError::InvalidConfig(
+                "reconnect max_attempts must be at least 1 (use ReconnectPolicy::Disabled instead)"
+                    .into(),
+            ));
+        }
+
         Ok(())
     }
 }
@@ -240,6 +630,50 @@ mod tests {
         assert!(config.validate().is_ok());
     }
 
+    #[test]
+    fn method_timeout_overrides_default() {
+        let config = ClientConfig::new()
+            .with_timeout(Duration::from_secs(2))
+            .with_method_timeout("eth_getLogsWithCursor", Duration::from_secs(60));
+
+        assert_eq!(config.timeout_for("eth_getLogsWithCursor"), Duration::from_secs(60));
+        assert_eq!(config.timeout_for("eth_blockNumber"), Duration::from_secs(2));
+
+        // The override stays in place when the default changes afterwards
+        let config = config.with_timeout(Duration::from_secs(5));
+        assert_eq!(config.timeout_for("eth_getLogsWithCursor"), Duration::from_secs(60));
+        assert_eq!(config.timeout_for("eth_blockNumber"), Duration::from_secs(5));
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn validate_method_timeout_and_retries() {
+        let config = ClientConfig::new().with_method_timeout("eth_blockNumber", Duration::ZERO);
+        assert!(config.validate().is_err());
+
+        let config = ClientConfig::new().with_max_retries(MAX_RETRIES + 1);
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn concurrency_limits() {
+        let config = ClientConfig::default();
+        assert_eq!(config.max_in_flight, DEFAULT_MAX_IN_FLIGHT);
+        assert_eq!(config.method_in_flight["eth_getLogsWithCursor"], DEFAULT_LOG_QUERIES_IN_FLIGHT);
+
+        let config = ClientConfig::new()
+            .with_max_in_flight(16)
+            .with_method_in_flight("eth_getLogsWithCursor", 4)
+            .with_max_queued(0);
+        assert_eq!(config.method_in_flight["eth_getLogsWithCursor"], 4);
+        assert!(config.validate().is_ok());
+
+        assert!(ClientConfig::new().with_max_in_flight(0).validate().is_err());
+        assert!(ClientConfig::new().with_max_in_flight(MAX_IN_FLIGHT + 1).validate().is_err());
+        assert!(ClientConfig::new().with_method_in_flight("eth_chainId", 0).validate().is_err());
+        assert!(ClientConfig::new().with_max_queued(MAX_QUEUED + 1).validate().is_err());
+    }
+
     #[test]
     fn validate_timeout_too_low() {
         let config = ClientConfig::new().with_timeout(Duration::from_millis(500));
@@ -303,4 +737,31 @@ mod tests {
         let config = ClientConfig::new().with_max_logs(MAX_LOGS_LIMIT);
         assert!(config.validate().is_ok());
     }
+
+    #[test]
+    fn ws_config_defaults() {
+        let config = WsConfig::default();
+        assert!(config.keepalive_interval < Duration::from_secs(30));
+        assert_eq!(
+            config.reconnect,
+            ReconnectPolicy::Auto {
+                max_attempts: DEFAULT_MAX_RECONNECT_ATTEMPTS,
+                delay: DEFAULT_RECONNECT_DELAY,
+            }
+        );
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn ws_config_validation() {
+        assert!(WsConfig::new().with_keepalive_interval(Duration::ZERO).validate().is_err());
+        assert!(WsConfig::new().with_subscribe_timeout(Duration::ZERO).validate().is_err());
+
+        let no_attempts = ReconnectPolicy::Auto {
+            max_attempts: 0,
+            delay: Duration::from_secs(1),
+        };
+        assert!(WsConfig::new().with_reconnect(no_attempts).validate().is_err());
+        assert!(WsConfig::new().with_reconnect(ReconnectPolicy::Disabled).validate().is_ok());
+    }
 }
@@ -5,6 +5,22 @@
 //! - [`MegaEthError`] - The primary error type for all client operations
 //! - Various error kinds for different failure modes (network, RPC, parsing)
 //!
+//! # MegaETH Error Codes
+//!
+//! Besides the standard JSON-RPC codes, MegaETH endpoints report limits of
+//! their own. They share generic codes with unrelated errors, so each is
+//! recognized by its code and message together:
+//!
+//! | Variant | Code | Message mentions | Structured data |
+//! |---------|------|------------------|-----------------|
+//! | [`BlockRangeTooLarge`](MegaEthError::BlockRangeTooLarge) | `-32005`, `-32602`, `-32000` | block range too large / exceeded | `maxRange`, or `max N` in the message |
+//! | [`ResourceLimitExceeded`](MegaEthError::ResourceLimitExceeded) | `-32005`, `-32000` | resource limit, response size, query too expensive | `suggestedRange { fromBlock, toBlock }`, or `[0x.., 0x..]` in the message |
+//! | [`CursorExpired`](MegaEthError::CursorExpired) | `-32602`, `-32000` | cursor expired / invalid / unknown | - |
+//! | [`RealtimeQueueFull`](MegaEthError::RealtimeQueueFull) | `-32005`, `-32000`, `-32603` | realtime queue full | - |
+//!
+//! Other `-32005` errors remain [`Rpc`](MegaEthError::Rpc) errors, which are
+//! rate limits and retried as such.
+//!
 //! # Error Philosophy
 //!
 //! These errors are designed to be:
@@ -13,7 +29,10 @@
 //! - **Informative**: Contains enough context for debugging without leaking secrets
 
 use std::fmt;
+use std::ops::RangeInclusive;
+use std::time::Duration;
 
+use sensitive::scrub_echo;
 use thiserror::Error;
 
 /// Result type alias using [`MegaEthError`].
@@ -29,10 +48,17 @@ pub type Result<T> = std::result::Result<T, MegaEthError>;
 ///
 /// | Category | Variants | Typical Cause |
 /// |----------|----------|---------------|
-/// | Network | `Connection`, `Timeout`, `Http` | Network issues, server down |
+/// | Network | `Connection`, `Timeout`, `Http`, `WebSocket` | Network issues, server down |
 /// | Protocol | `Rpc`, `MethodNotSupported` | Server rejected request |
-/// | Data | `Serialization`, `InvalidResponse` | Malformed data |
+/// | Limits | `BlockRangeTooLarge`, `ResourceLimitExceeded`, `CursorExpired`, `RealtimeQueueFull` | Server-side caps |
+/// | Data | `Serialization`, `InvalidResponse`, `ResponseTooLarge` | Malformed data |
+/// | Load | `ClientOverloaded` | Too many requests at once |
 /// | Usage | `InvalidConfig` | Programmer error |
+///
+/// Errors of [`MegaEthClient`](crate::MegaEthClient) calls arrive wrapped in
+/// [`MegaEthError::Request`], which adds the method, attempt and elapsed time.
+/// The predicates below look through the wrapper; use [`inner`](Self::inner)
+/// to match on the underlying variant.
 #[derive(Debug, Error)]
 #[non_exhaustive]
 pub enum MegaEthError {
@@ -53,6 +79,10 @@ pub enum MegaEthError {
     #[error("HTTP error: {0}")]
     Http(String),
 
+    /// WebSocket protocol error (handshake failure, broken frame, etc.).
+    #[error("WebSocket error: {0}")]
+    WebSocket(String),
+
     /// JSON-RPC error returned by the server.
     ///
     /// Contains the error code and message from the RPC response.
@@ -77,6 +107,52 @@ pub enum MegaEthError {
         method: String,
     },
 
+    /// The block range of a log query is wider than the endpoint allows.
+    ///
+    /// Retrying the same range fails again; query at most `max_range` blocks
+    /// at a time. [`MegaEthClient::get_logs_with_cursor`](crate::MegaEthClient::get_logs_with_cursor)
+    /// narrows its range by itself.
+    #[error("block range too large: {message}")]
+    BlockRangeTooLarge {
+        /// Most blocks one query may span, if the server said.
+        max_range: Option<u64>,
+        /// Human-readable error message from the server.
+        message: String,
+    },
+
+    /// A log query needs more server resources than one request may use.
+    ///
+    /// Retrying the same range fails again; query a smaller one, such as
+    /// `suggested_smaller_range`. [`MegaEthClient::get_logs_with_cursor`](crate::MegaEthClient::get_logs_with_cursor)
+    /// narrows its range by itself.
+    #[error("resource limit exceeded: {message}")]
+    ResourceLimitExceeded {
+        /// Block range the server suggests querying instead, if it did.
+        suggested_smaller_range: Option<RangeInclusive<u64>>,
+        /// Human-readable error message from the server.
+        message: String,
+    },
+
+    /// The endpoint's realtime transaction queue is full.
+    ///
+    /// The transaction was not accepted, so it is safe to send again after
+    /// a backoff.
+    #[error("realtime queue full: {message}")]
+    RealtimeQueueFull {
+        /// Human-readable error message from the server.
+        message: String,
+    },
+
+    /// The server no longer knows the pagination cursor.
+    ///
+    /// Cursors only live for a while. Query again without a cursor, from the
+    /// last block whose logs were all received.
+    #[error("cursor expired: {message}")]
+    CursorExpired {
+        /// Human-readable error message from the server.
+        message: String,
+    },
+
     /// Failed to serialize request or deserialize response.
     #[error("serialization error: {0}")]
     Serialization(#[from] serde_json::Error),
@@ -118,6 +194,47 @@ pub enum MegaEthError {
         /// Maximum allowed logs.
         max: usize,
     },
+
+    /// Response body exceeded the configured size limit.
+    ///
+    /// The response was aborted while streaming instead of being buffered.
+    /// See [`ClientConfig::max_response_bytes`](crate::ClientConfig::max_response_bytes).
+    #[error("response to {method} too large: {bytes} bytes (limit {limit})")]
+    ResponseTooLarge {
+        /// The method whose response was too large.
+        method: String,
+        /// Bytes received (or announced by the server) when the response was aborted.
+        bytes: usize,
+        /// Configured limit.
+        limit: usize,
+    },
+
+    /// The client's request queue was full, so the request was not sent.
+    ///
+    /// Every permit of [`ClientConfig::max_in_flight`](crate::ClientConfig::max_in_flight)
+    /// was taken and [`ClientConfig::max_queued`](crate::ClientConfig::max_queued)
+    /// requests already waited for one. The client does not retry it; send
+    /// fewer requests at once, or back off before sending again.
+    #[error("client overloaded: {method} shed with {max_queued} requests queued")]
+    ClientOverloaded {
+        /// The method of the request that was shed.
+        method: String,
+        /// Configured queue limit.
+        max_queued: usize,
+    },
+
+    /// A request failed; wraps the underlying error with request context.
+    #[error("{method} failed on attempt {attempt} after {elapsed:?}: {source}")]
+    Request {
+        /// JSON-RPC method name.
+        method: String,
+        /// Attempt that failed, starting at 1.
+        attempt: u32,
+        /// Time since the first attempt started.
+        elapsed: Duration,
+        /// The underlying error.
+        source: Box<Self>,
+    },
 }
 
 impl MegaEthError {
@@ -141,13 +258,22 @@ impl MegaEthError {
         }
     }
 
+    /// The underlying error, with any [`MegaEthError::Request`] context removed.
+    #[must_use]
+    pub fn inner(&self) -> &Self {
+        match self {
+            Self::Request { source, .. } => source.inner(),
+            other => other,
+        }
+    }
+
     /// Check if this error indicates the method is not supported.
     ///
     /// Returns `true` for both [`MegaEthError::MethodNotSupported`] and
     /// [`MegaEthError::Rpc`] with method-not-found error codes.
     #[must_use]
-    pub const fn is_method_not_supported(&self) -> bool {
-        match self {
+    pub fn is_method_not_supported(&self) -> bool {
+        match self.inner() {
             Self::MethodNotSupported { .. } => true,
             Self::Rpc { code, .. } => {
                 // -32601 = Method not found (JSON-RPC standard)
@@ -161,11 +287,14 @@ impl MegaEthError {
     /// Check if this error is likely transient and retryable.
     ///
     /// Returns `true` for network issues, timeouts, and server-side errors
-    /// that might succeed on retry.
+    /// that might succeed on retry. Range and cursor errors fail again until
+    /// the query changes, so they are not retryable.
     #[must_use]
     pub fn is_retryable(&self) -> bool {
-        match self {
-            Self::Connection(_) | Self::Timeout => true,
+        match self.inner() {
+            Self::Connection(_) | Self::Timeout | Self::WebSocket(_) | Self::RealtimeQueueFull { .. } => {
+                true
+            }
             Self::Http(msg) => {
                 // 5xx errors are typically retryable
                 msg.contains("500")
@@ -184,7 +313,7 @@ impl MegaEthError {
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
-// CONVERSIONS FROM reqwest ERRORS
+// CONVERSIONS FROM TRANSPORT ERRORS
 // ═══════════════════════════════════════════════════════════════════════════════
 
 impl From<reqwest::Error> for MegaEthError {
@@ -192,11 +321,25 @@ impl From<reqwest::Error> for MegaEthError {
         if err.is_timeout() {
             Self::Timeout
         } else if err.is_connect() {
-            Self::Connection(err.to_string())
+            Self::Connection(scrub_echo(&err.to_string()))
         } else if err.is_request() || err.is_body() || err.is_decode() {
-            Self::Http(err.to_string())
+            Self::Http(scrub_echo(&err.to_string()))
         } else {
-            Self::Connection(err.to_string())
+            Self::Connection(scrub_echo(&err.to_string()))
+        }
+    }
+}
+
+impl From<tokio_tungstenite::tungstenite::Error> for MegaEthError {
+    fn from(err: tokio_tungstenite::tungstenite::Error) -> Self {
+        use tokio_tungstenite::tungstenite::Error as WsError;
+
+        match err {
+            WsError::ConnectionClosed | WsError::AlreadyClosed => {
+                Self::Connection("WebSocket connection closed".into())
+            }
+            WsError::Io(e) => Self::Connection(e.to_string()),
+            other => Self::WebSocket(other.to_string()),
         }
     }
 }
@@ -221,6 +364,10 @@ pub(crate) struct RpcErrorDetail {
 
 impl RpcErrorDetail {
     /// Convert this detail into a [`MegaEthError`].
+    ///
+    /// A generic RPC error keeps its message and data without the long hex
+    /// payloads or keys the server echoed from the request, such as a raw
+    /// transaction.
     pub fn into_error(self, method: &str) -> MegaEthError {
         // Check for method not supported
         if self.code == -32601 || self.code == -32600 {
@@ -228,15 +375,100 @@ impl RpcErrorDetail {
                 method: method.to_string(),
             };
         }
+        if let Some(error) = self.classify() {
+            return error;
+        }
 
         MegaEthError::Rpc {
             code: self.code,
-            message: self.message,
-            data: self.data.map(|v| v.to_string()),
+            message: scrub_echo(&self.message),
+            data: self.data.map(|v| scrub_echo(&v.to_string())),
+        }
+    }
+
+    /// The MegaETH-specific error this is, see the
+    /// [module docs](self#megaeth-error-codes).
+    fn classify(&self) -> Option<MegaEthError> {
+        if !matches!(self.code, -32005 | -32602 | -32603 | -32000) {
+            return None;
+        }
+        let lower = self.message.to_lowercase();
+        let message = self.message.clone();
+        let mentions = |words: &[&str]| words.iter().any(|word| lower.contains(word));
+
+        if lower.contains("cursor") && mentions(&["expired", "invalid", "unknown", "not found"]) {
+            return Some(MegaEthError::CursorExpired { message });
+        }
+        if lower.contains("queue") && mentions(&["full", "saturated"]) {
+            return Some(MegaEthError::RealtimeQueueFull { message });
+        }
+        if self.code == -32603 {
+            return None;
         }
+        // Resource errors often name a range that would work
+        if self.code != -32602 && mentions(&["resource", "too expensive", "response size", "too many logs"]) {
+            let suggested_smaller_range = self.suggested_range().or_else(|| range_in(&self.message));
+            return Some(MegaEthError::ResourceLimitExceeded {
+                suggested_smaller_range,
+                message,
+            });
+        }
+        if lower.contains("range") && mentions(&["too large", "too wide", "exceed", "max"]) {
+            let max_range = self
+                .data_field(&["maxRange", "max_range", "maxBlockRange"])
+                .and_then(block_number)
+                .or_else(|| number_after(&lower, "max"));
+            return Some(MegaEthError::BlockRangeTooLarge { max_range, message });
+        }
+        None
+    }
+
+    /// The first of `keys` in the error data.
+    fn data_field(&self, keys: &[&str]) -> Option<&serde_json::Value> {
+        let data = self.data.as_ref()?;
+        keys.iter().find_map(|key| data.get(*key))
+    }
+
+    /// The `suggestedRange` in the error data.
+    fn suggested_range(&self) -> Option<RangeInclusive<u64>> {
+        let range = self.data_field(&["suggestedRange", "suggested_range"])?;
+        let from = block_number(range.get("fromBlock").or_else(|| range.get("from"))?)?;
+        let to = block_number(range.get("toBlock").or_else(|| range.get("to"))?)?;
+        (from <= to).then_some(from..=to)
     }
 }
 
+/// A block number given as a JSON number, a hex string or a decimal string.
+fn block_number(value: &serde_json::Value) -> Option<u64> {
+    match value {
+        serde_json::Value::Number(number) => number.as_u64(),
+        serde_json::Value::String(text) => parse_number(text),
+        _ => None,
+    }
+}
+
+fn parse_number(text: &str) -> Option<u64> {
+    text.strip_prefix("0x")
+        .map_or_else(|| text.parse().ok(), |hex| u64::from_str_radix(hex, 16).ok())
+}
+
+/// The first number following `word` in `message`, as in "max 10000 blocks".
+fn number_after(message: &str, word: &str) -> Option<u64> {
+    let (_, rest) = message.split_once(word)?;
+    rest.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|token| token.starts_with(|c: char| c.is_ascii_digit()))
+        .find_map(parse_number)
+}
+
+/// A block range written as `[0x100, 0x1ff]` in `message`.
+fn range_in(message: &str) -> Option<RangeInclusive<u64>> {
+    let (_, rest) = message.split_once('[')?;
+    let (range, _) = rest.split_once(']')?;
+    let (from, to) = range.split_once(',')?;
+    let (from, to) = (parse_number(from.trim())?, parse_number(to.trim())?);
+    (from <= to).then_some(from..=to)
+}
+
 impl fmt::Display for RpcErrorDetail {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "RPC error ({}): {}", self.code, self.message)
@@ -286,6 +518,15 @@ mod tests {
 
         let serialization = MegaEthError::InvalidResponse("missing field".into());
         assert!(!serialization.is_retryable());
+
+        let websocket = MegaEthError::WebSocket("unexpected EOF".into());
+        assert!(websocket.is_retryable());
+
+        let overloaded = MegaEthError::ClientOverloaded {
+            method: "eth_getLogsWithCursor".into(),
+            max_queued: 64,
+        };
+        assert!(!overloaded.is_retryable());
     }
 
     #[test]
@@ -315,4 +556,123 @@ mod tests {
         let error = detail.into_error("eth_getLogsWithCursor");
         assert!(matches!(error, MegaEthError::MethodNotSupported { method } if method == "eth_getLogsWithCursor"));
     }
+
+    #[test]
+    fn request_context_in_display() {
+        let error = MegaEthError::Request {
+            method: "eth_getLogsWithCursor".into(),
+            attempt: 3,
+            elapsed: Duration::from_millis(1500),
+            source: Box::new(MegaEthError::Timeout),
+        };
+
+        assert_eq!(
+            error.to_string(),
+            "eth_getLogsWithCursor failed on attempt 3 after 1.5s: request timed out"
+        );
+        // Predicates look through the context
+        assert!(error.is_retryable());
+        assert!(matches!(error.inner(), MegaEthError::Timeout));
+    }
+
+    /// The error for a canned JSON-RPC error object.
+    fn canned(json: serde_json::Value) -> MegaEthError {
+        let detail: RpcErrorDetail = serde_json::from_value(json).expect("parse failed");
+        detail.into_error("eth_getLogsWithCursor")
+    }
+
+    #[test]
+    fn megaeth_range_errors_are_typed() {
+        let error = canned(serde_json::json!({
+            "code": -32005,
+            "message": "block range too large",
+            "data": {"maxRange": 10000}
+        }));
+        assert!(matches!(error, MegaEthError::BlockRangeTooLarge { max_range: Some(10_000), .. }));
+
+        // Without data, the limit is read from the message
+        let error = canned(serde_json::json!({
+            "code": -32602,
+            "message": "Block range exceeds max of 0x1388 blocks"
+        }));
+        assert!(matches!(error, MegaEthError::BlockRangeTooLarge { max_range: Some(5_000), .. }));
+
+        let error = canned(serde_json::json!({
+            "code": -32005,
+            "message": "query exceeds resource limits",
+            "data": {"suggestedRange": {"fromBlock": "0x100", "toBlock": "0x17f"}}
+        }));
+        assert!(matches!(
+            &error,
+            MegaEthError::ResourceLimitExceeded { suggested_smaller_range: Some(range), .. }
+                if *range == (0x100..=0x17f)
+        ));
+
+        let error = canned(serde_json::json!({
+            "code": -32000,
+            "message": "Log response size exceeded. This block range should work: [0x100, 0x13f]"
+        }));
+        assert!(matches!(
+            &error,
+            MegaEthError::ResourceLimitExceeded { suggested_smaller_range: Some(range), .. }
+                if *range == (0x100..=0x13f)
+        ));
+
+        for error in [
+            error,
+            MegaEthError::BlockRangeTooLarge { max_range: None, message: String::new() },
+        ] {
+            assert!(!error.is_retryable());
+            assert!(!error.is_method_not_supported());
+        }
+    }
+
+    #[test]
+    fn megaeth_cursor_and_queue_errors_are_typed() {
+        let expired = canned(serde_json::json!({"code": -32602, "message": "cursor expired"}));
+        assert!(matches!(expired, MegaEthError::CursorExpired { .. }));
+        assert!(!expired.is_retryable());
+
+        let unknown = canned(serde_json::json!({"code": -32000, "message": "Invalid cursor: 0x0001"}));
+        assert!(matches!(unknown, MegaEthError::CursorExpired { .. }));
+
+        let full = canned(serde_json::json!({"code": -32603, "message": "realtime queue is full"}));
+        assert!(matches!(&full, MegaEthError::RealtimeQueueFull { message } if message.contains("full")));
+        assert!(full.is_retryable());
+        assert!(!full.is_method_not_supported());
+
+        // Other limit errors are rate limits, and internal errors stay generic
+        let limited = canned(serde_json::json!({"code": -32005, "message": "rate limit exceeded"}));
+        assert!(matches!(limited, MegaEthError::Rpc { code: -32005, .. }));
+        assert!(limited.is_retryable());
+        let internal = canned(serde_json::json!({"code": -32603, "message": "block range exceeded"}));
+        assert!(matches!(internal, MegaEthError::Rpc { code: -32603, .. }));
+    }
+    #[test]
+    fn echoed_requests_are_scrubbed() {
+        let raw_tx = format!("0x02f8b1{}", "ab".repeat(180));
+        let error = canned(serde_json::json!({
+            "code": -32000,
+            "message": format!("invalid transaction: {raw_tx}"),
+            "data": { "params": [raw_tx] },
+        }));
+        let echoed = "0x02f8b1abab…[356 hex digits]";
+        assert!(
+            matches!(&error, MegaEthError::Rpc { message, data: Some(data), .. }
+                if *message == format!("invalid transaction: {echoed}")
+                    && *data == format!(r#"{{"params":["{echoed}"]}}"#)),
+            "{error:?}"
+        );
+
+        let key = "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+        let error = canned(serde_json::json!({
+            "code": -32000,
+            "message": format!("invalid signer key {key}"),
+        }));
+        assert!(
+            matches!(&error, MegaEthError::Rpc { message, .. }
+                if message == "invalid signer key [redacted]"),
+            "{error:?}"
+        );
+    }
 }
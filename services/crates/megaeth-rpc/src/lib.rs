@@ -1,11 +1,13 @@
 //! MegaETH-specific JSON-RPC client with cursor pagination and realtime API support.
 //!
 //! This crate provides [`MegaEthClient`], a specialized RPC client for MegaETH's
-//! extended JSON-RPC API. It handles the unique characteristics of MegaETH:
+//! extended JSON-RPC API, and [`MegaEthWsClient`] for its WebSocket
+//! subscriptions. They handle the unique characteristics of MegaETH:
 //!
 //! - **High throughput**: MegaETH processes ~1000 TPS, generating massive data volumes
 //! - **Cursor pagination**: `eth_getLogsWithCursor` for efficient large-range queries
 //! - **Realtime API**: `realtime_sendRawTransaction` for instant receipts (~10ms)
+//! - **Realtime subscriptions**: `miniBlocks`, `stateChanges` and `logs` streams
 //!
 //! # Crate Relationships
 //!
@@ -77,8 +79,10 @@
 //!
 //! - **Cursor-based pagination**: Automatic multi-batch fetching for large queries
 //! - **Realtime transactions**: Submit and get receipt in ~10ms
+//! - **Realtime subscriptions**: Typed streams with keep-alive and reconnects
 //! - **Graceful fallback detection**: Check if extended APIs are available
 //! - **Configurable**: Timeouts, batch limits, log limits, and more
+//! - **Bounded concurrency**: Capped requests in flight, per method too, with load shedding
 //! - **Fully typed**: All requests and responses have proper Rust types
 //!
 //! # Memory Considerations
@@ -91,8 +95,9 @@
 //! use megaeth_rpc::ClientConfig;
 //!
 //! let config = ClientConfig::default()
-//!     .with_max_logs(100_000)        // Limit total logs collected
-//!     .with_max_cursor_batches(50);  // Limit RPC round-trips
+//!     .with_max_logs(100_000)                    // Limit total logs collected
+//!     .with_max_cursor_batches(50)               // Limit RPC round-trips
+//!     .with_max_response_bytes(16 * 1024 * 1024); // Abort oversized responses
 //! ```
 //!
 //! **Memory estimation:** Each log is approximately 200-500 bytes. 100,000 logs ≈ 20-50 MB.
@@ -100,7 +105,9 @@
 //! # Modules
 //!
 //! - [`client`] - The main [`MegaEthClient`] implementation
-//! - [`config`] - Configuration options via [`ClientConfig`]
+//! - [`ws`] - WebSocket subscriptions via [`MegaEthWsClient`]
+//! - [`config`] - Configuration options via [`ClientConfig`] and [`WsConfig`]
+//! - [`limiter`] - Concurrency limits and [`ClientStats`] of client requests
 //! - [`types`] - Request/response types for MegaETH RPC methods
 //! - [`error`] - Error types with detailed context
 //!
@@ -110,6 +117,8 @@
 //! |--------|-------------|---------------------|
 //! | `eth_getLogsWithCursor` | Paginated log queries | `eth_getLogs` |
 //! | `realtime_sendRawTransaction` | Instant receipts | `eth_sendRawTransaction` + polling |
+//! | `eth_subscribe("miniBlocks")` | Mini block stream (~10ms) | `eth_subscribe("newHeads")` |
+//! | `eth_subscribe("stateChanges")` | Account state stream | Polling `eth_getBalance` etc. |
 //!
 //! # Error Handling
 //!
@@ -132,6 +141,7 @@
 //! ```
 
 #![doc(html_root_url = "https://docs.ghostnet.io/megaeth-rpc")]
+#![cfg_attr(test, allow(clippy::unwrap_used, clippy::expect_used))]
 
 // ═══════════════════════════════════════════════════════════════════════════════
 // MODULES
@@ -140,7 +150,9 @@
 pub mod client;
 pub mod config;
 pub mod error;
+pub mod limiter;
 pub mod types;
+pub mod ws;
 
 // ═══════════════════════════════════════════════════════════════════════════════
 // RE-EXPORTS
@@ -148,9 +160,14 @@ pub mod types;
 
 // Primary types - what most users need
 pub use client::MegaEthClient;
-pub use config::ClientConfig;
+pub use config::{ClientConfig, ReconnectPolicy, WsConfig};
 pub use error::{MegaEthError, Result};
-pub use types::{FetchStats, LogsWithCursorFilter, LogsWithCursorResponse, RealtimeResponse};
+pub use limiter::{ClientStats, MethodStats};
+pub use types::{
+    FetchStats, LogsWithCursorFilter, LogsWithCursorResponse, MiniBlock, RealtimeResponse,
+    StateChange,
+};
+pub use ws::{MegaEthWsClient, Subscription};
 
 // ═══════════════════════════════════════════════════════════════════════════════
 // CRATE INFO
@@ -0,0 +1,326 @@
+//! Concurrency limiting of [`MegaEthClient`](crate::MegaEthClient) requests.
+//!
+//! Every request takes a permit before it is sent, and gives it back once its
+//! response is read:
+//!
+//! - At most [`ClientConfig::max_in_flight`] requests are in flight at once.
+//! - Methods with a cap of their own in [`ClientConfig::method_in_flight`],
+//!   such as `eth_getLogsWithCursor`, take a permit of that cap first. They
+//!   never hold more of the shared permits than their cap, so a burst of log
+//!   queries leaves the rest to cheap calls.
+//! - Requests that find no permit free wait for one, first come first
+//!   served. Once [`ClientConfig::max_queued`] requests wait, further ones
+//!   fail with [`MegaEthError::ClientOverloaded`] instead of queuing.
+//!
+//! The limiter keeps the [`ClientStats`] of [`MegaEthClient::stats`](crate::MegaEthClient::stats).
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+use tokio::sync::{Semaphore, SemaphorePermit};
+use tracing::warn;
+
+use crate::config::ClientConfig;
+use crate::error::{MegaEthError, Result};
+
+/// Queue waits the p95 is taken over, the most recent ones.
+const WAIT_SAMPLES: usize = 1024;
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// STATISTICS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Statistics of the requests a client sent to its endpoint.
+///
+/// Counts cover every attempt since the client was created, retries
+/// included.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ClientStats {
+    /// Requests in flight right now.
+    pub in_flight: usize,
+
+    /// Most requests that were in flight at once.
+    pub in_flight_high_water: usize,
+
+    /// Requests waiting for a permit right now.
+    pub queued: usize,
+
+    /// 95th percentile of the time requests waited for a permit, over the
+    /// last 1024 requests.
+    pub queue_wait_p95: Duration,
+
+    /// Requests sent.
+    pub requests: u64,
+
+    /// Requests shed with [`MegaEthError::ClientOverloaded`].
+    pub shed: u64,
+
+    /// Statistics per JSON-RPC method.
+    pub methods: BTreeMap<String, MethodStats>,
+}
+
+/// Statistics of the requests of one JSON-RPC method.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MethodStats {
+    /// Requests sent.
+    pub requests: u64,
+
+    /// Requests shed with [`MegaEthError::ClientOverloaded`].
+    pub shed: u64,
+}
+
+/// What the limiter records beyond its counters.
+#[derive(Debug, Default)]
+struct Recorder {
+    /// Most requests in flight at once.
+    high_water: usize,
+
+    /// Latest queue waits, oldest first.
+    waits: VecDeque<Duration>,
+
+    /// Counts per method.
+    methods: BTreeMap<String, MethodStats>,
+}
+
+impl Recorder {
+    /// Record a request of `method` sent after waiting `wait`, with
+    /// `in_flight` requests in flight.
+    fn sent(&mut self, method: &str, wait: Duration, in_flight: usize) {
+        self.high_water = self.high_water.max(in_flight);
+        if self.waits.len() == WAIT_SAMPLES {
+            self.waits.pop_front();
+        }
+        self.waits.push_back(wait);
+        self.method(method).requests += 1;
+    }
+
+    fn method(&mut self, method: &str) -> &mut MethodStats {
+        self.methods.entry(method.to_string()).or_default()
+    }
+
+    /// 95th percentile of the recorded waits.
+    fn wait_p95(&self) -> Duration {
+        let mut waits: Vec<_> = self.waits.iter().copied().collect();
+        waits.sort_unstable();
+        let rank = (waits.len() * 95).div_ceil(100);
+        rank.checked_sub(1)
+            .map_or(Duration::ZERO, |index| waits[index])
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// REQUEST LIMITER
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Permits of a client's requests, see the [module docs](self).
+#[derive(Debug)]
+pub(crate) struct RequestLimiter {
+    /// Permits shared by every request.
+    permits: Semaphore,
+
+    /// Permits of the methods with a cap of their own.
+    method_permits: HashMap<String, Semaphore>,
+
+    /// Most requests waiting for a permit.
+    max_queued: usize,
+
+    /// Requests waiting for a permit.
+    queued: AtomicUsize,
+
+    /// Requests holding a permit.
+    in_flight: AtomicUsize,
+
+    /// Statistics beyond the counters above.
+    recorder: Mutex<Recorder>,
+}
+
+impl RequestLimiter {
+    /// Limiter with the caps of `config`.
+    pub(crate) fn new(config: &ClientConfig) -> Self {
+        let method_permits = config
+            .method_in_flight
+            .iter()
+            .map(|(method, max)| (method.clone(), Semaphore::new(*max)))
+            .collect();
+        Self {
+            permits: Semaphore::new(config.max_in_flight),
+            method_permits,
+            max_queued: config.max_queued,
+            queued: AtomicUsize::new(0),
+            in_flight: AtomicUsize::new(0),
+            recorder: Mutex::default(),
+        }
+    }
+
+    /// Take the permits a request of `method` needs, waiting for them if
+    /// none are free.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MegaEthError::ClientOverloaded`] if the request would have
+    /// to wait while `max_queued` requests already do.
+    pub(crate) async fn acquire(&self, method: &str) -> Result<RequestPermit<'_>> {
+        let started = Instant::now();
+        let own = self.method_permits.get(method);
+
+        // A request that finds its permits free does not queue
+        let free = own.is_none_or(|permits| permits.available_permits() > 0)
+            && self.permits.available_permits() > 0;
+        let queued = if free {
+            None
+        } else {
+            Some(self.enqueue(method)?)
+        };
+
+        // Always in this order, so no request holds a shared permit while
+        // it waits for its method's
+        let method_permit = match own {
+            Some(permits) => Some(permits.acquire().await.map_err(|_| closed())?),
+            None => None,
+        };
+        let permit = self.permits.acquire().await.map_err(|_| closed())?;
+        drop(queued);
+
+        let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        self.recorder().sent(method, started.elapsed(), in_flight);
+        Ok(RequestPermit {
+            _method: method_permit,
+            _shared: permit,
+            in_flight: &self.in_flight,
+        })
+    }
+
+    /// Join the queue, unless it is full.
+    fn enqueue(&self, method: &str) -> Result<Queued<'_>> {
+        let depth = self.queued.fetch_add(1, Ordering::SeqCst) + 1;
+        let queued = Queued(&self.queued);
+        if depth > self.max_queued {
+            self.recorder().method(method).shed += 1;
+            warn!(
+                method,
+                max_queued = self.max_queued,
+                "Request queue full, shedding request"
+            );
+            return Err(MegaEthError::ClientOverloaded {
+                method: method.to_string(),
+                max_queued: self.max_queued,
+            });
+        }
+        Ok(queued)
+    }
+
+    /// Statistics of the requests so far.
+    pub(crate) fn stats(&self) -> ClientStats {
+        let recorder = self.recorder();
+        let (high_water, wait_p95) = (recorder.high_water, recorder.wait_p95());
+        let methods = recorder.methods.clone();
+        drop(recorder);
+        ClientStats {
+            in_flight: self.in_flight.load(Ordering::SeqCst),
+            in_flight_high_water: high_water,
+            queued: self.queued.load(Ordering::SeqCst),
+            queue_wait_p95: wait_p95,
+            requests: methods.values().map(|stats| stats.requests).sum(),
+            shed: methods.values().map(|stats| stats.shed).sum(),
+            methods,
+        }
+    }
+
+    /// Lock the recorder, recovering it if a holder panicked.
+    fn recorder(&self) -> MutexGuard<'_, Recorder> {
+        self.recorder.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// The error of a permit of a closed semaphore; the limiter never closes
+/// its semaphores.
+fn closed() -> MegaEthError {
+    MegaEthError::Connection("request limiter closed".into())
+}
+
+/// Place of a request in the queue, left once dropped.
+struct Queued<'a>(&'a AtomicUsize);
+
+impl Drop for Queued<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Permits of a request in flight, given back once dropped.
+#[derive(Debug)]
+pub(crate) struct RequestPermit<'a> {
+    /// Permit of the method's own cap, if it has one.
+    _method: Option<SemaphorePermit<'a>>,
+
+    /// Permit of the shared cap.
+    _shared: SemaphorePermit<'a>,
+
+    /// The limiter's count of requests in flight.
+    in_flight: &'a AtomicUsize,
+}
+
+impl Drop for RequestPermit<'_> {
+    fn drop(&mut self) {
+        self.in_flight.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wait_p95_of_recent_requests() {
+        let mut recorder = Recorder::default();
+        assert_eq!(recorder.wait_p95(), Duration::ZERO);
+
+        for millis in 1..=100 {
+            recorder.sent("eth_chainId", Duration::from_millis(millis), 1);
+        }
+        assert_eq!(recorder.wait_p95(), Duration::from_millis(95));
+
+        // Only the latest waits count
+        for _ in 0..WAIT_SAMPLES {
+            recorder.sent("eth_chainId", Duration::ZERO, 1);
+        }
+        assert_eq!(recorder.wait_p95(), Duration::ZERO);
+        assert_eq!(recorder.methods["eth_chainId"].requests, 1124);
+    }
+
+    #[tokio::test]
+    async fn methods_with_a_cap_leave_shared_permits_free() {
+        let config = ClientConfig::default()
+            .with_max_in_flight(3)
+            .with_method_in_flight("eth_getLogsWithCursor", 1);
+        let limiter = RequestLimiter::new(&config);
+
+        let log_query = limiter
+            .acquire("eth_getLogsWithCursor")
+            .await
+            .expect("permit is free");
+        let first = limiter
+            .acquire("eth_chainId")
+            .await
+            .expect("permit is free");
+        let second = limiter
+            .acquire("eth_chainId")
+            .await
+            .expect("permit is free");
+        assert_eq!(limiter.stats().in_flight, 3);
+
+        drop((log_query, first, second));
+        let stats = limiter.stats();
+        assert_eq!(
+            (stats.in_flight, stats.in_flight_high_water, stats.requests),
+            (0, 3, 3)
+        );
+        assert_eq!(stats.methods["eth_getLogsWithCursor"].requests, 1);
+    }
+}
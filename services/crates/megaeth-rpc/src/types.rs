@@ -6,10 +6,14 @@
 //! - [`LogsWithCursorResponse`] - Response from cursor-based queries
 //! - [`FetchStats`] - Statistics from paginated fetch operations
 //! - [`RealtimeResponse`] - Response from realtime transaction submission
+//! - [`MiniBlock`] - Item of the `miniBlocks` subscription
+//! - [`StateChange`] - Item of the `stateChanges` subscription
 
-use alloy::primitives::{Address, TxHash, B256};
+use std::collections::HashMap;
+
+use alloy::primitives::{Address, TxHash, B256, U256};
 use alloy::rpc::types::Log;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 
 // ═══════════════════════════════════════════════════════════════════════════════
 // CURSOR-BASED LOG PAGINATION
@@ -136,6 +140,18 @@ pub struct FetchStats {
     ///
     /// If `false`, the fetch was stopped early (e.g., due to batch limit).
     pub complete: bool,
+
+    /// Bytes sent in request bodies, including retries.
+    pub request_bytes: usize,
+
+    /// Bytes received in response bodies, including retries.
+    pub response_bytes: usize,
+
+    /// Times the block range was narrowed after the server rejected it.
+    pub range_splits: usize,
+
+    /// Times the query started over after its cursor expired.
+    pub cursor_restarts: usize,
 }
 
 impl Default for FetchStats {
@@ -144,6 +160,10 @@ impl Default for FetchStats {
             total_logs: 0,
             batches: 0,
             complete: true,
+            request_bytes: 0,
+            response_bytes: 0,
+            range_splits: 0,
+            cursor_restarts: 0,
         }
     }
 }
@@ -156,6 +176,10 @@ impl FetchStats {
             total_logs: log_count,
             batches: 1,
             complete: true,
+            request_bytes: 0,
+            response_bytes: 0,
+            range_splits: 0,
+            cursor_restarts: 0,
         }
     }
 }
@@ -238,6 +262,96 @@ impl RealtimeResponse {
     }
 }
 
+// ═══════════════════════════════════════════════════════════════════════════════
+// REALTIME SUBSCRIPTIONS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Mini block from the `miniBlocks` subscription.
+///
+/// Mini blocks are produced every ~10ms and contain preconfirmed transactions
+/// along with their receipts.
+///
+/// **Note:** The field names of the live API differ from the documentation.
+/// The API returns `number` and `timestamp` where the docs say
+/// `mini_block_number` and `mini_block_timestamp`; both spellings are
+/// accepted. All numeric fields are JSON integers, not hex strings.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct MiniBlock {
+    /// The EVM block number this mini block belongs to.
+    pub block_number: u64,
+
+    /// Timestamp of the EVM block (Unix seconds).
+    pub block_timestamp: u64,
+
+    /// Index of this mini block within the EVM block.
+    pub index: u64,
+
+    /// Mini block number in blockchain history.
+    #[serde(alias = "mini_block_number")]
+    pub number: u64,
+
+    /// When this mini block was created (Unix microseconds).
+    #[serde(alias = "mini_block_timestamp")]
+    pub timestamp: u64,
+
+    /// Gas used in this mini block.
+    pub gas_used: u64,
+
+    /// Transactions included (same schema as `eth_getTransactionByHash`).
+    #[serde(default)]
+    pub transactions: Vec<serde_json::Value>,
+
+    /// Receipts of the transactions (same schema as `eth_getTransactionReceipt`).
+    #[serde(default)]
+    pub receipts: Vec<serde_json::Value>,
+}
+
+/// Account state change from the `stateChanges` subscription.
+///
+/// Emitted for monitored accounts as soon as a transaction affecting them is
+/// packaged into a mini block. Only the changed parts of the state are set.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct StateChange {
+    /// Account whose state changed.
+    pub address: Address,
+
+    /// New nonce, if it changed.
+    #[serde(default, deserialize_with = "deserialize_quantity")]
+    pub nonce: Option<u64>,
+
+    /// New native balance, if it changed.
+    #[serde(default)]
+    pub balance: Option<U256>,
+
+    /// Changed storage slots and their new values.
+    #[serde(default)]
+    pub storage: HashMap<B256, B256>,
+}
+
+/// Deserialize an optional quantity given as a JSON integer or hex string.
+fn deserialize_quantity<'de, D>(deserializer: D) -> std::result::Result<Option<u64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Quantity {
+        Number(u64),
+        Hex(String),
+    }
+
+    match Option::<Quantity>::deserialize(deserializer)? {
+        None => Ok(None),
+        Some(Quantity::Number(n)) => Ok(Some(n)),
+        Some(Quantity::Hex(hex)) => {
+            let digits = hex.strip_prefix("0x").unwrap_or(&hex);
+            u64::from_str_radix(digits, 16)
+                .map(Some)
+                .map_err(serde::de::Error::custom)
+        }
+    }
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // INTERNAL TYPES
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -271,6 +385,27 @@ pub(crate) struct JsonRpcResponse<T> {
     pub error: Option<crate::error::RpcErrorDetail>,
 }
 
+/// Any message received on a WebSocket: a response to one of our requests
+/// (with `id`) or a subscription notification (with `params`).
+#[derive(Debug, Deserialize)]
+pub(crate) struct WsMessage {
+    #[serde(default)]
+    pub id: Option<u64>,
+    #[serde(default)]
+    pub result: Option<serde_json::Value>,
+    #[serde(default)]
+    pub error: Option<crate::error::RpcErrorDetail>,
+    #[serde(default)]
+    pub params: Option<SubscriptionNotification>,
+}
+
+/// Payload of an `eth_subscription` notification.
+#[derive(Debug, Deserialize)]
+pub(crate) struct SubscriptionNotification {
+    pub subscription: String,
+    pub result: serde_json::Value,
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // TESTS
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -390,4 +525,47 @@ mod tests {
         let response: RealtimeResponse = serde_json::from_str(json).expect("parse failed");
         assert!(!response.is_success());
     }
+
+    #[test]
+    fn mini_block_accepts_live_and_documented_field_names() {
+        let live = r#"{
+            "block_number": 100, "block_timestamp": 1700000000, "index": 3,
+            "number": 12345, "timestamp": 1700000000123456, "gas_used": 21000,
+            "transactions": [{}], "receipts": [{}]
+        }"#;
+        let documented = r#"{
+            "block_number": 100, "block_timestamp": 1700000000, "index": 3,
+            "mini_block_number": 12345, "mini_block_timestamp": 1700000000123456,
+            "gas_used": 21000
+        }"#;
+
+        let live: MiniBlock = serde_json::from_str(live).expect("parse failed");
+        let documented: MiniBlock = serde_json::from_str(documented).expect("parse failed");
+
+        assert_eq!(live.number, 12345);
+        assert_eq!(live.transactions.len(), 1);
+        assert_eq!(documented.number, live.number);
+        assert_eq!(documented.timestamp, live.timestamp);
+        assert!(documented.receipts.is_empty());
+    }
+
+    #[test]
+    fn state_change_deserialization() {
+        let json = r#"{
+            "address": "0x000000000000000000000000000000000000dead",
+            "nonce": "0x2a",
+            "balance": "0xde0b6b3a7640000"
+        }"#;
+
+        let change: StateChange = serde_json::from_str(json).expect("parse failed");
+        assert_eq!(change.nonce, Some(42));
+        assert_eq!(change.balance, Some(U256::from(10).pow(U256::from(18))));
+        assert!(change.storage.is_empty());
+
+        let numeric: StateChange =
+            serde_json::from_str(r#"{"address": "0x000000000000000000000000000000000000dead", "nonce": 7}"#)
+                .expect("parse failed");
+        assert_eq!(numeric.nonce, Some(7));
+        assert!(numeric.balance.is_none());
+    }
 }
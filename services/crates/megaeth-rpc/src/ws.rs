@@ -0,0 +1,719 @@
+//! WebSocket subscriptions to MegaETH's Realtime API.
+//!
+//! This module provides [`MegaEthWsClient`] for the `eth_subscribe` streams
+//! that MegaETH serves over WebSocket:
+//!
+//! - **`miniBlocks`**: Every mini block (~10ms) with transactions and receipts
+//! - **`stateChanges`**: Balance, nonce and storage changes of given accounts
+//! - **`logs`**: Contract logs, at mini block granularity with `pending` tags
+//!
+//! # Features
+//!
+//! - **Typed items**: Subscriptions are [`Stream`]s of [`MiniBlock`],
+//!   [`StateChange`] or [`Log`]
+//! - **Confirmation handling**: Subscribing only succeeds once the server has
+//!   confirmed the subscription, so rejected subscriptions surface as errors
+//! - **Keep-alive**: MegaETH drops connections after 30 seconds without
+//!   activity, so every connection sends periodic `eth_chainId` requests and
+//!   answers the server's pings
+//! - **Binary frames**: Some endpoints send notifications as binary frames
+//!   containing UTF-8 JSON; these are decoded like text frames
+//! - **Pluggable reconnects**: Reconnect automatically or surface the error,
+//!   see [`ReconnectPolicy`]
+//!
+//! # Example
+//!
+//! ```ignore
+//! use futures::StreamExt;
+//! use megaeth_rpc::MegaEthWsClient;
+//!
+//! let client = MegaEthWsClient::new("wss://mainnet.megaeth.com/ws")?;
+//!
+//! let mut mini_blocks = client.subscribe_mini_blocks().await?;
+//! while let Some(mini_block) = mini_blocks.next().await {
+//!     let mini_block = mini_block?;
+//!     println!("Mini block {} in block {}", mini_block.number, mini_block.block_number);
+//! }
+//! ```
+
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use alloy::primitives::Address;
+use alloy::rpc::types::{Filter, Log};
+use futures::{SinkExt, Stream, StreamExt};
+use serde::de::DeserializeOwned;
+use tokio::net::TcpStream;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio::time::{Instant, interval_at, timeout};
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream, connect_async};
+use tracing::{debug, info, instrument, warn};
+
+use crate::config::{ReconnectPolicy, WsConfig};
+use crate::error::{MegaEthError, Result};
+use crate::types::{JsonRpcRequest, MiniBlock, StateChange, WsMessage};
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// CONSTANTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Request ID of the `eth_subscribe` request on each connection.
+const SUBSCRIBE_REQUEST_ID: u64 = 1;
+
+/// Items buffered per subscription before the connection stops reading.
+const SUBSCRIPTION_BUFFER: usize = 1024;
+
+/// WebSocket connection to a MegaETH endpoint.
+type WsStream = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// MEGAETH WEBSOCKET CLIENT
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// MegaETH Realtime API client for WebSocket subscriptions.
+///
+/// Each subscription runs on its own connection, driven by a background task
+/// that keeps it alive and reconnects according to the configured
+/// [`ReconnectPolicy`]. Dropping the [`Subscription`] closes the connection.
+///
+/// # Example
+///
+/// ```ignore
+/// use megaeth_rpc::{MegaEthWsClient, ReconnectPolicy, WsConfig};
+///
+/// // Surface disconnects instead of reconnecting, e.g. to backfill gaps
+/// let config = WsConfig::default().with_reconnect(ReconnectPolicy::Disabled);
+/// let client = MegaEthWsClient::with_config("wss://mainnet.megaeth.com/ws", config)?;
+///
+/// let changes = client.subscribe_state_changes(vec![account]).await?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct MegaEthWsClient {
+    /// WebSocket endpoint URL.
+    ws_url: String,
+
+    /// Client configuration.
+    config: WsConfig,
+}
+
+impl MegaEthWsClient {
+    /// Create a new WebSocket client with default configuration.
+    ///
+    /// No connection is made until the first subscription.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MegaEthError::InvalidConfig`] if the URL is not a `ws://` or
+    /// `wss://` URL.
+    pub fn new(ws_url: impl Into<String>) -> Result<Self> {
+        Self::with_config(ws_url, WsConfig::default())
+    }
+
+    /// Create a new WebSocket client with custom configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MegaEthError::InvalidConfig`] if the URL is not a `ws://` or
+    /// `wss://` URL or the configuration is invalid.
+    pub fn with_config(ws_url: impl Into<String>, config: WsConfig) -> Result<Self> {
+        config.validate()?;
+
+        let ws_url = ws_url.into();
+        if !ws_url.starts_with("ws://") && !ws_url.starts_with("wss://") {
+            return Err(MegaEthError::InvalidConfig(format!(
+                "WebSocket URL must start with ws:// or wss://, got {ws_url}"
+            )));
+        }
+
+        Ok(Self { ws_url, config })
+    }
+
+    /// Get the WebSocket URL this client connects to.
+    #[must_use]
+    pub fn ws_url(&self) -> &str {
+        &self.ws_url
+    }
+
+    /// Get the current configuration.
+    #[must_use]
+    pub const fn config(&self) -> &WsConfig {
+        &self.config
+    }
+
+    /// Subscribe to mini blocks.
+    ///
+    /// # Errors
+    ///
+    /// - [`MegaEthError::Connection`] or [`MegaEthError::WebSocket`] if the
+    ///   connection fails
+    /// - [`MegaEthError::Timeout`] if connecting or the confirmation times out
+    /// - [`MegaEthError::MethodNotSupported`] or [`MegaEthError::Rpc`] if the
+    ///   server rejects the subscription
+    pub async fn subscribe_mini_blocks(&self) -> Result<Subscription<MiniBlock>> {
+        self.subscribe(serde_json::json!(["miniBlocks"])).await
+    }
+
+    /// Subscribe to state changes of the given accounts.
+    ///
+    /// # Errors
+    ///
+    /// See [`subscribe_mini_blocks`](Self::subscribe_mini_blocks).
+    pub async fn subscribe_state_changes(
+        &self,
+        addresses: Vec<Address>,
+    ) -> Result<Subscription<StateChange>> {
+        self.subscribe(serde_json::json!(["stateChanges", addresses]))
+            .await
+    }
+
+    /// Subscribe to logs matching a filter.
+    ///
+    /// Use `pending` block tags for mini block latency (~10ms).
+    ///
+    /// # Errors
+    ///
+    /// See [`subscribe_mini_blocks`](Self::subscribe_mini_blocks).
+    pub async fn subscribe_logs(&self, filter: &Filter) -> Result<Subscription<Log>> {
+        self.subscribe(serde_json::json!(["logs", filter])).await
+    }
+
+    /// Subscribe with raw `eth_subscribe` parameters.
+    ///
+    /// Notification payloads are deserialized into `T`; payloads that don't
+    /// match are yielded as errors without ending the subscription.
+    ///
+    /// # Errors
+    ///
+    /// See [`subscribe_mini_blocks`](Self::subscribe_mini_blocks).
+    #[instrument(skip(self), fields(ws_url = %self.ws_url))]
+    pub async fn subscribe<T>(&self, params: serde_json::Value) -> Result<Subscription<T>>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        let (ws, id) = open(&self.ws_url, &params, &self.config).await?;
+        info!(subscription = %id, "Subscription confirmed");
+
+        let (sender, receiver) = mpsc::channel(SUBSCRIPTION_BUFFER);
+        let session = Session {
+            ws_url: self.ws_url.clone(),
+            params,
+            config: self.config.clone(),
+            sender,
+        };
+        let task = tokio::spawn(session.run(ws, id.clone()));
+
+        Ok(Subscription { id, receiver, task })
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// SUBSCRIPTION
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Stream of subscription items.
+///
+/// Yields `Err` for notifications that can't be decoded and, unless
+/// reconnecting automatically, when the connection is lost; the stream ends
+/// after a connection error. Dropping the subscription closes its connection.
+#[derive(Debug)]
+pub struct Subscription<T> {
+    /// Subscription ID confirmed by the server.
+    id: String,
+
+    /// Items from the connection task.
+    receiver: mpsc::Receiver<Result<T>>,
+
+    /// Task driving the connection.
+    task: JoinHandle<()>,
+}
+
+impl<T> Subscription<T> {
+    /// Get the subscription ID the server confirmed.
+    ///
+    /// After an automatic reconnect the server assigns a new ID; this stays
+    /// the ID of the initial subscription.
+    #[must_use]
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+}
+
+impl<T> Stream for Subscription<T> {
+    type Item = Result<T>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().receiver.poll_recv(cx)
+    }
+}
+
+impl<T> Drop for Subscription<T> {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// CONNECTION HANDLING
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Connect and subscribe, returning the connection and the subscription ID.
+async fn open(
+    ws_url: &str,
+    params: &serde_json::Value,
+    config: &WsConfig,
+) -> Result<(WsStream, String)> {
+    let (mut ws, _response) = timeout(config.connect_timeout, connect_async(ws_url))
+        .await
+        .map_err(|_| MegaEthError::Timeout)??;
+    debug!("WebSocket connected");
+
+    let request = JsonRpcRequest::new("eth_subscribe", params, SUBSCRIBE_REQUEST_ID);
+    ws.send(Message::text(serde_json::to_string(&request)?))
+        .await?;
+
+    let id = timeout(config.subscribe_timeout, await_confirmation(&mut ws))
+        .await
+        .map_err(|_| MegaEthError::Timeout)??;
+    Ok((ws, id))
+}
+
+/// Wait for the response to the `eth_subscribe` request.
+async fn await_confirmation(ws: &mut WsStream) -> Result<String> {
+    while let Some(message) = ws.next().await {
+        let Some(text) = payload(message?)? else {
+            continue;
+        };
+
+        let message: WsMessage = serde_json::from_str(&text)?;
+        if message.id != Some(SUBSCRIBE_REQUEST_ID) {
+            continue;
+        }
+        if let Some(error) = message.error {
+            return Err(error.into_error("eth_subscribe"));
+        }
+        return match message.result {
+            Some(serde_json::Value::String(id)) => Ok(id),
+            other => Err(MegaEthError::InvalidResponse(format!(
+                "expected subscription ID, got {other:?}"
+            ))),
+        };
+    }
+
+    Err(MegaEthError::Connection(
+        "WebSocket closed before the subscription was confirmed".into(),
+    ))
+}
+
+/// Text payload of a data frame.
+///
+/// Returns `None` for control frames: pings are answered by the WebSocket
+/// implementation on the next read. Binary frames are decoded as UTF-8.
+fn payload(message: Message) -> Result<Option<String>> {
+    match message {
+        Message::Text(text) => Ok(Some(text.to_string())),
+        Message::Binary(data) => String::from_utf8(data.to_vec())
+            .map(Some)
+            .map_err(|e| MegaEthError::InvalidResponse(format!("binary frame is not UTF-8: {e}"))),
+        Message::Close(frame) => Err(MegaEthError::Connection(format!(
+            "WebSocket closed by server: {frame:?}"
+        ))),
+        Message::Ping(_) | Message::Pong(_) | Message::Frame(_) => Ok(None),
+    }
+}
+
+/// Background task state of one subscription.
+struct Session<T> {
+    /// WebSocket endpoint URL, for reconnecting.
+    ws_url: String,
+
+    /// `eth_subscribe` parameters, for re-subscribing.
+    params: serde_json::Value,
+
+    /// Client configuration.
+    config: WsConfig,
+
+    /// Items for the [`Subscription`].
+    sender: mpsc::Sender<Result<T>>,
+}
+
+impl<T: DeserializeOwned> Session<T> {
+    /// Forward notifications until the subscription is dropped or the
+    /// connection is lost for good.
+    async fn run(self, mut ws: WsStream, mut id: String) {
+        loop {
+            let error = match self.forward(&mut ws, &id).await {
+                // Subscription dropped
+                Ok(()) => return,
+                Err(e) => e,
+            };
+
+            let ReconnectPolicy::Auto {
+                max_attempts,
+                delay,
+            } = self.config.reconnect
+            else {
+                warn!(error = %error, "WebSocket subscription lost");
+                let _ = self.sender.send(Err(error)).await;
+                return;
+            };
+
+            warn!(error = %error, "WebSocket subscription lost, reconnecting");
+            let mut attempt = 0;
+            loop {
+                attempt += 1;
+                tokio::select! {
+                    () = self.sender.closed() => return,
+                    () = tokio::time::sleep(delay) => {}
+                }
+
+                match open(&self.ws_url, &self.params, &self.config).await {
+                    Ok((new_ws, new_id)) => {
+                        info!(attempt, subscription = %new_id, "WebSocket subscription restored");
+                        ws = new_ws;
+                        id = new_id;
+                        break;
+                    }
+                    Err(e) if attempt >= max_attempts => {
+                        warn!(attempt, error = %e, "Giving up reconnecting WebSocket subscription");
+                        let _ = self.sender.send(Err(e)).await;
+                        return;
+                    }
+                    Err(e) => {
+                        warn!(attempt, max = max_attempts, error = %e, "WebSocket reconnect failed");
+                    }
+                }
+            }
+        }
+    }
+
+    /// Forward the notifications of one connection.
+    ///
+    /// Returns `Ok` once the subscription is dropped and `Err` when the
+    /// connection fails.
+    async fn forward(&self, ws: &mut WsStream, id: &str) -> Result<()> {
+        let period = self.config.keepalive_interval;
+        let mut keepalive = interval_at(Instant::now() + period, period);
+        let mut request_id = SUBSCRIBE_REQUEST_ID;
+
+        loop {
+            tokio::select! {
+                () = self.sender.closed() => return Ok(()),
+
+                _ = keepalive.tick() => {
+                    request_id += 1;
+                    let ping = JsonRpcRequest::new("eth_chainId", [(); 0], request_id);
+                    ws.send(Message::text(serde_json::to_string(&ping)?)).await?;
+                    debug!(request_id, "Keep-alive sent");
+                }
+
+                message = ws.next() => {
+                    let message = message.ok_or_else(|| {
+                        MegaEthError::Connection("WebSocket stream ended".into())
+                    })??;
+                    let Some(item) = payload(message)?.and_then(|text| decode(&text, id)) else {
+                        continue;
+                    };
+                    if self.sender.send(item).await.is_err() {
+                        return Ok(());
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Decode a message into a subscription item.
+///
+/// Returns `None` for messages that are not notifications of subscription
+/// `id`, such as keep-alive responses.
+fn decode<T: DeserializeOwned>(text: &str, id: &str) -> Option<Result<T>> {
+    let message: WsMessage = match serde_json::from_str(text) {
+        Ok(message) => message,
+        Err(e) => return Some(Err(e.into())),
+    };
+
+    if let Some(error) = message.error {
+        warn!(request_id = ?message.id, error = %error, "WebSocket request failed");
+        return None;
+    }
+
+    let notification = message.params?;
+    if notification.subscription != id {
+        debug!(subscription = %notification.subscription, "Ignoring foreign notification");
+        return None;
+    }
+    Some(serde_json::from_value(notification.result).map_err(Into::into))
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+mod tests {
+    use std::future::Future;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use tokio::net::TcpListener;
+    use tokio_tungstenite::accept_async;
+
+    use super::*;
+
+    /// Server side of a mock connection.
+    type ServerWs = WebSocketStream<TcpStream>;
+
+    /// Start a mock WebSocket server calling `handler` with the index and
+    /// stream of each accepted connection.
+    async fn serve<F, Fut>(handler: F) -> String
+    where
+        F: Fn(usize, ServerWs) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        let connections = Arc::new(AtomicUsize::new(0));
+
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let index = connections.fetch_add(1, Ordering::SeqCst);
+                let ws = accept_async(stream).await.unwrap();
+                tokio::spawn(handler(index, ws));
+            }
+        });
+        url
+    }
+
+    /// Read the `eth_subscribe` request and confirm it as `id`.
+    async fn confirm(ws: &mut ServerWs, id: &str) -> serde_json::Value {
+        let request = next_json(ws).await;
+        assert_eq!(request["method"], "eth_subscribe");
+        let response = serde_json::json!({"jsonrpc": "2.0", "id": request["id"], "result": id});
+        ws.send(Message::text(response.to_string())).await.unwrap();
+        request
+    }
+
+    /// Next text frame as JSON, skipping control frames.
+    async fn next_json(ws: &mut ServerWs) -> serde_json::Value {
+        loop {
+            if let Message::Text(text) = ws.next().await.unwrap().unwrap() {
+                return serde_json::from_str(&text).unwrap();
+            }
+        }
+    }
+
+    fn notification(id: &str, result: &serde_json::Value) -> String {
+        serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": "eth_subscription",
+            "params": {"subscription": id, "result": result}
+        })
+        .to_string()
+    }
+
+    fn mini_block(number: u64) -> serde_json::Value {
+        serde_json::json!({
+            "block_number": 100,
+            "block_timestamp": 1_700_000_000,
+            "index": number % 100,
+            "number": number,
+            "timestamp": 1_700_000_000_000_000_u64 + number,
+            "gas_used": 21000,
+            "transactions": [],
+            "receipts": []
+        })
+    }
+
+    fn client(url: &str, reconnect: ReconnectPolicy) -> MegaEthWsClient {
+        let config = WsConfig::default()
+            .with_connect_timeout(Duration::from_secs(2))
+            .with_subscribe_timeout(Duration::from_secs(2))
+            .with_reconnect(reconnect);
+        MegaEthWsClient::with_config(url, config).unwrap()
+    }
+
+    async fn next<T>(subscription: &mut Subscription<T>) -> Option<Result<T>> {
+        timeout(Duration::from_secs(5), subscription.next())
+            .await
+            .expect("timed out waiting for item")
+    }
+
+    #[test]
+    fn rejects_http_urls() {
+        assert!(MegaEthWsClient::new("https://carrot.megaeth.com/rpc").is_err());
+        assert!(MegaEthWsClient::new("wss://carrot.megaeth.com/ws").is_ok());
+    }
+
+    #[tokio::test]
+    async fn confirms_subscription_and_streams_items() {
+        let url = serve(|_, mut ws| async move {
+            let request = confirm(&mut ws, "0xmini").await;
+            assert_eq!(request["params"], serde_json::json!(["miniBlocks"]));
+
+            for message in [
+                notification("0xmini", &mini_block(1)),
+                // Keep-alive response and another subscription's data are skipped
+                r#"{"jsonrpc":"2.0","id":2,"result":"0x1b58"}"#.to_string(),
+                notification("0xother", &mini_block(99)),
+                notification("0xmini", &serde_json::json!({"unexpected": true})),
+                notification("0xmini", &mini_block(2)),
+            ] {
+                ws.send(Message::text(message)).await.unwrap();
+            }
+            // Keep the connection open
+            let _ = ws.next().await;
+        })
+        .await;
+
+        let mut mini_blocks = client(&url, ReconnectPolicy::Disabled)
+            .subscribe_mini_blocks()
+            .await
+            .unwrap();
+
+        assert_eq!(mini_blocks.id(), "0xmini");
+        assert_eq!(next(&mut mini_blocks).await.unwrap().unwrap().number, 1);
+        // Undecodable payloads are errors but don't end the subscription
+        assert!(next(&mut mini_blocks).await.unwrap().is_err());
+        assert_eq!(next(&mut mini_blocks).await.unwrap().unwrap().number, 2);
+    }
+
+    #[tokio::test]
+    async fn rejected_subscription_is_an_error() {
+        let url = serve(|_, mut ws| async move {
+            let request = next_json(&mut ws).await;
+            let response = serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": request["id"],
+                "error": {"code": -32601, "message": "Method not found"}
+            });
+            ws.send(Message::text(response.to_string())).await.unwrap();
+        })
+        .await;
+
+        let result = client(&url, ReconnectPolicy::Disabled)
+            .subscribe_state_changes(vec![Address::ZERO])
+            .await;
+
+        assert!(result.unwrap_err().is_method_not_supported());
+    }
+
+    #[tokio::test]
+    async fn decodes_binary_frames() {
+        let url = serve(|_, mut ws| async move {
+            let request = confirm(&mut ws, "0xstate").await;
+            assert_eq!(request["params"][0], "stateChanges");
+
+            let change = serde_json::json!({
+                "address": "0x000000000000000000000000000000000000dead",
+                "balance": "0x1"
+            });
+            let frame = notification("0xstate", &change).into_bytes();
+            ws.send(Message::binary(frame)).await.unwrap();
+            let _ = ws.next().await;
+        })
+        .await;
+
+        let mut changes = client(&url, ReconnectPolicy::Disabled)
+            .subscribe_state_changes(vec![Address::ZERO])
+            .await
+            .unwrap();
+
+        let change = next(&mut changes).await.unwrap().unwrap();
+        assert_eq!(change.balance, Some(alloy::primitives::U256::from(1)));
+    }
+
+    #[tokio::test]
+    async fn answers_pings_and_sends_keepalives() {
+        let url = serve(|_, mut ws| async move {
+            confirm(&mut ws, "0xlogs").await;
+            ws.send(Message::Ping(b"still there?".to_vec().into()))
+                .await
+                .unwrap();
+
+            let (mut ponged, mut kept_alive) = (false, false);
+            while !(ponged && kept_alive) {
+                match ws.next().await.unwrap().unwrap() {
+                    Message::Pong(data) => ponged = data.as_ref() == b"still there?",
+                    Message::Text(text) => kept_alive = text.contains("eth_chainId"),
+                    _ => {}
+                }
+            }
+
+            // Report success through the subscription
+            let log = serde_json::json!({
+                "address": "0x1234567890123456789012345678901234567890",
+                "topics": [],
+                "data": "0x",
+                "blockNumber": "0x100",
+                "transactionHash": "0x0000000000000000000000000000000000000000000000000000000000000001",
+                "transactionIndex": "0x0",
+                "blockHash": "0x0000000000000000000000000000000000000000000000000000000000000000",
+                "logIndex": "0x0",
+                "removed": false
+            });
+            ws.send(Message::text(notification("0xlogs", &log)))
+                .await
+                .unwrap();
+            let _ = ws.next().await;
+        })
+        .await;
+
+        let config = WsConfig::default()
+            .with_keepalive_interval(Duration::from_millis(50))
+            .with_reconnect(ReconnectPolicy::Disabled);
+        let client = MegaEthWsClient::with_config(url, config).unwrap();
+        let mut logs = client.subscribe_logs(&Filter::new()).await.unwrap();
+
+        let log = next(&mut logs).await.unwrap().unwrap();
+        assert_eq!(log.block_number, Some(0x100));
+    }
+
+    #[tokio::test]
+    async fn disconnect_is_surfaced_without_reconnect() {
+        let url = serve(|_, mut ws| async move {
+            confirm(&mut ws, "0xmini").await;
+            ws.close(None).await.unwrap();
+        })
+        .await;
+
+        let mut mini_blocks = client(&url, ReconnectPolicy::Disabled)
+            .subscribe_mini_blocks()
+            .await
+            .unwrap();
+
+        assert!(next(&mut mini_blocks).await.unwrap().is_err());
+        assert!(next(&mut mini_blocks).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn reconnects_and_resubscribes() {
+        let url = serve(|index, mut ws| async move {
+            if index == 0 {
+                confirm(&mut ws, "0xfirst").await;
+                ws.close(None).await.unwrap();
+                return;
+            }
+            confirm(&mut ws, "0xsecond").await;
+            ws.send(Message::text(notification("0xsecond", &mini_block(7))))
+                .await
+                .unwrap();
+            let _ = ws.next().await;
+        })
+        .await;
+
+        let reconnect = ReconnectPolicy::Auto {
+            max_attempts: 3,
+            delay: Duration::from_millis(10),
+        };
+        let mut mini_blocks = client(&url, reconnect)
+            .subscribe_mini_blocks()
+            .await
+            .unwrap();
+
+        assert_eq!(next(&mut mini_blocks).await.unwrap().unwrap().number, 7);
+        assert_eq!(mini_blocks.id(), "0xfirst");
+    }
+}
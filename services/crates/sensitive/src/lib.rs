@@ -0,0 +1,327 @@
+//! Values kept out of logs and error messages.
+//!
+//! [`Sensitive`] wraps a secret, such as a private key or a signed
+//! transaction, so that formatting it for a log line or an error prints
+//! `[redacted]` instead of its contents. The value is only reached through
+//! [`expose`](Sensitive::expose), which makes every use of it visible.
+//!
+//! [`redact`] is the defense in depth for values that were never wrapped: it
+//! scrubs text of what looks like a private key or a raw transaction, and is
+//! meant for log output as it is written. [`scrub_echo`] does the same for
+//! error messages in which a node echoes the request it refused, and keeps
+//! them short.
+//!
+//! | Pattern | Redacted |
+//! |---------|----------|
+//! | 64 hex digits without `0x` | always, the usual form of a private key |
+//! | `0x` and 64 hex digits | after a name with `key`, `secret`, `seed`, `mnemonic` or `password` in it |
+//! | 200 or more hex digits | always, a raw transaction or full calldata |
+//!
+//! Transaction and block hashes (`0x` and 64 digits) and addresses are
+//! left alone, as a log without them is of little use.
+
+use std::borrow::Cow;
+use std::fmt::{self, Write as _};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// What a redacted value is replaced with.
+pub const REDACTED: &str = "[redacted]";
+
+/// Hex digits of a private key.
+const KEY_HEX_DIGITS: usize = 64;
+
+/// Fewest hex digits of a run taken for a raw transaction or calldata.
+const PAYLOAD_HEX_DIGITS: usize = 200;
+
+/// Parts of a name marking the value after it as a secret.
+const SECRET_NAMES: &[&str] = &["key", "secret", "seed", "mnemonic", "password"];
+
+/// Longest run of hex digits an error message keeps whole. Longer runs are
+/// payloads a node echoed from the request, like a raw transaction.
+const MAX_HEX_RUN: usize = 128;
+
+/// Hex digits kept of a longer run, enough to tell a selector or a
+/// transaction type.
+const KEPT_HEX_DIGITS: usize = 10;
+
+/// Longest error message kept, in characters.
+const MAX_MESSAGE_CHARS: usize = 512;
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// SENSITIVE
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// A secret whose `Debug` and `Display` print `[redacted]`.
+///
+/// Serialization is transparent, so a wrapped config value still reads from
+/// and writes to its file as before; only serialize it where the secret is
+/// meant to be stored.
+///
+/// ```
+/// use sensitive::Sensitive;
+///
+/// let key = Sensitive::new("0xac09...".to_string());
+/// assert_eq!(format!("{key:?}"), "[redacted]");
+/// assert_eq!(key.expose(), "0xac09...");
+/// ```
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct Sensitive<T>(T);
+
+impl<T> Sensitive<T> {
+    /// Wrap a secret.
+    #[must_use]
+    pub const fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    /// The secret itself, to use it.
+    #[must_use]
+    pub const fn expose(&self) -> &T {
+        &self.0
+    }
+
+    /// Unwrap the secret.
+    #[must_use]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for Sensitive<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T> fmt::Debug for Sensitive<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+impl<T> fmt::Display for Sensitive<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+impl<T: Serialize> Serialize for Sensitive<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Sensitive<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize(deserializer).map(Self)
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// REDACTION
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Replace what looks like a private key or a raw transaction in `text` with
+/// `[redacted]`, see the [module docs](self).
+///
+/// Only whole words are matched, so hex inside a longer identifier is kept.
+#[must_use]
+pub fn redact(text: &str) -> Cow<'_, str> {
+    let bytes = text.as_bytes();
+    let mut redacted = String::new();
+    let mut copied = 0;
+    let mut at = 0;
+    while at < bytes.len() {
+        if !is_word(bytes[at]) {
+            at += 1;
+            continue;
+        }
+        let start = at;
+        while at < bytes.len() && is_word(bytes[at]) {
+            at += 1;
+        }
+        if is_secret(bytes, start, at) {
+            redacted.push_str(&text[copied..start]);
+            redacted.push_str(REDACTED);
+            copied = at;
+        }
+    }
+    if copied == 0 {
+        return Cow::Borrowed(text);
+    }
+    redacted.push_str(&text[copied..]);
+    Cow::Owned(redacted)
+}
+
+/// Check whether the word `bytes[start..end]` is a secret.
+fn is_secret(bytes: &[u8], start: usize, end: usize) -> bool {
+    let word = &bytes[start..end];
+    let (prefixed, digits) = match word {
+        [b'0', b'x' | b'X', digits @ ..] => (true, digits),
+        digits => (false, digits),
+    };
+    if !digits.iter().all(u8::is_ascii_hexdigit) {
+        return false;
+    }
+    digits.len() >= PAYLOAD_HEX_DIGITS
+        || (digits.len() == KEY_HEX_DIGITS && (!prefixed || names_secret(&bytes[..start])))
+}
+
+/// Check whether the name `before` ends with, as in `private_key=` or
+/// `"seed": "`, is that of a secret.
+fn names_secret(before: &[u8]) -> bool {
+    let end = before.len()
+        - before
+            .iter()
+            .rev()
+            .take_while(|&&b| matches!(b, b'=' | b':' | b'"' | b'\'' | b' '))
+            .count();
+    let name = &before[..end];
+    let start = name
+        .iter()
+        .rposition(|&b| !is_word(b))
+        .map_or(0, |at| at + 1);
+    let name = name[start..].to_ascii_lowercase();
+    SECRET_NAMES.iter().any(|secret| {
+        name.windows(secret.len())
+            .any(|part| part == secret.as_bytes())
+    })
+}
+
+/// Check whether `byte` belongs to a word: an ASCII letter, digit or `_`.
+const fn is_word(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'_'
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// ECHOED REQUESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Scrub what an error `message` echoes of a request.
+///
+/// Hex runs beyond [`MAX_HEX_RUN`] digits are cut to their first digits,
+/// what is left is [redacted](redact), and everything past
+/// [`MAX_MESSAGE_CHARS`] is dropped.
+#[must_use]
+pub fn scrub_echo(message: &str) -> String {
+    let mut truncated = String::with_capacity(message.len());
+    let mut rest = message;
+    while let Some(start) = rest.find(|c: char| c.is_ascii_hexdigit()) {
+        let end = rest[start..]
+            .find(|c: char| !c.is_ascii_hexdigit())
+            .map_or(rest.len(), |len| start + len);
+        truncated.push_str(&rest[..start]);
+        let digits = &rest[start..end];
+        if digits.len() > MAX_HEX_RUN {
+            let (kept, cut) = digits.split_at(KEPT_HEX_DIGITS);
+            let _ = write!(truncated, "{kept}…[{} hex digits]", cut.len());
+        } else {
+            truncated.push_str(digits);
+        }
+        rest = &rest[end..];
+    }
+    truncated.push_str(rest);
+
+    let mut scrubbed = redact(&truncated).into_owned();
+    if let Some((at, _)) = scrubbed.char_indices().nth(MAX_MESSAGE_CHARS) {
+        scrubbed.truncate(at);
+        scrubbed.push('…');
+    }
+    scrubbed
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    const KEY: &str = "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+    const HASH: &str = "0x88df016429689c079f3b2f6ad39fa052532c56795b733da78a91ebe6a713944b";
+
+    #[test]
+    fn sensitive_values_format_redacted() {
+        let key = Sensitive::new(KEY.to_string());
+        assert_eq!(format!("{key}"), REDACTED);
+        assert_eq!(format!("{key:?}"), REDACTED);
+        assert_eq!(format!("{:?}", Some(key.clone())), "Some([redacted])");
+        assert_eq!(key.expose(), KEY);
+        assert_eq!(key.into_inner(), KEY);
+    }
+
+    #[test]
+    fn sensitive_values_serialize_transparently() {
+        let key: Sensitive<String> = serde_json::from_str(&format!("\"{KEY}\"")).unwrap();
+        assert_eq!(key.expose(), KEY);
+        assert_eq!(serde_json::to_string(&key).unwrap(), format!("\"{KEY}\""));
+    }
+
+    #[test]
+    fn redacts_keys_and_raw_transactions() {
+        assert_eq!(
+            redact(&format!("key {KEY} loaded")),
+            "key [redacted] loaded"
+        );
+        assert_eq!(
+            redact(&format!("private_key=0x{KEY} wallet=w1")),
+            "private_key=[redacted] wallet=w1"
+        );
+        assert_eq!(
+            redact(&format!(r#"{{"seed_phrase":"0x{KEY}"}}"#)),
+            r#"{"seed_phrase":"[redacted]"}"#
+        );
+
+        let raw_tx = format!("0x02f8b1{}", "ab".repeat(120));
+        assert_eq!(redact(&format!("sending {raw_tx}")), "sending [redacted]");
+    }
+
+    #[test]
+    fn keeps_hashes_and_addresses() {
+        let line = format!(
+            "tx_hash={HASH} block_hash={HASH} to=0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266 \
+             selector=0xa9059cbb amount=1000000000000000000"
+        );
+        assert!(matches!(redact(&line), Cow::Borrowed(_)));
+        assert_eq!(
+            redact(&format!("{{\"hash\":\"{HASH}\"}}")),
+            format!("{{\"hash\":\"{HASH}\"}}")
+        );
+
+        // A key name further back does not make a hash a secret
+        let line = format!("key=w1 tx_hash={HASH}");
+        assert_eq!(redact(&line), line);
+    }
+
+    #[test]
+    fn echoed_requests_are_scrubbed() {
+        let raw_tx = format!("0x02f8b1{}", "ab".repeat(180));
+        assert_eq!(
+            scrub_echo(&format!("invalid transaction: {raw_tx}")),
+            "invalid transaction: 0x02f8b1abab…[356 hex digits]"
+        );
+        assert_eq!(
+            scrub_echo(&format!("invalid key {KEY}")),
+            "invalid key [redacted]"
+        );
+
+        let known = format!("already known: {HASH}");
+        assert_eq!(scrub_echo(&known), known);
+
+        let long = scrub_echo(&"gas ".repeat(200));
+        assert_eq!(long.chars().count(), MAX_MESSAGE_CHARS + 1);
+        assert!(long.ends_with('…'));
+    }
+
+    #[test]
+    fn matches_whole_words_only() {
+        let id = format!("wallet_{KEY}");
+        assert_eq!(redact(&id), id);
+        let longer = format!("{KEY}00");
+        assert_eq!(redact(&longer), longer);
+        assert_eq!(redact(&format!("é {KEY} é")), "é [redacted] é");
+    }
+}
@@ -9,28 +9,101 @@
 //! [service]
 //! name = "ghost-fleet"
 //! tick_interval_ms = 1000
+//! default_chain = "testnet"
 //!
-//! [chain]
+//! [chains.testnet]
 //! chain_id = 6343
 //! rpc_url = "https://carrot.megaeth.com/rpc"
 //!
+//! [chains.testnet.ghostnet]
+//! ghost_core = "0x..."
+//!
 //! [plugins]
 //! enabled = ["ghostnet"]
 //!
-//! [plugins.ghostnet]
-//! ghost_core = "0x..."
+//! [plugins.health]
+//! max_error_rate = 0.5
+//! ```
+//!
+//! # Chain Profiles
+//!
+//! A single-chain deployment can put its chain in `[chain]`. To target
+//! several deployments with one config, define named profiles in
+//! `[chains.<name>]` and select one with `--chain <name>` or
+//! `service.default_chain`; [`Settings::select_chain`] then makes it the
+//! active `chain`. Contract addresses come from the chain's `ghostnet` table,
+//! overridden by its `addresses_file` deployment manifest if set.
+//!
+//! # Behavior Profiles
+//!
+//! Wallets refer to a profile of `[profiles.<name>]`. A profile either sets
+//! its fields directly, falling back to the defaults of
+//! [`BehaviorProfile::new`], or sets `extends` to a preset (`whale`,
+//! `grinder`, `degen`, `casual`, `sniper`) or another profile and overrides a
+//! subset of the fields:
+//!
+//! ```toml
+//! [profiles.patient_whale]
+//! extends = "whale"
+//! patience = 1.0
+//! ```
+//!
+//! [`Settings::profile_catalog`] resolves them into concrete profiles.
+//!
+//! # Fleets
+//!
+//! One process can run several independent fleets side by side, each in a
+//! `[fleet.<name>]` table with its own wallets and optionally its own
+//! profiles and plugin configuration:
+//!
+//! ```toml
+//! [fleet.alpha]
+//! wallets = [{ id = "w1", address = "0x...", profile = "whale" }]
+//!
+//! [fleet.beta.plugins]
+//! enabled = ["ghostnet"]
 //! ```
+//!
+//! Everything else, the chain in particular, is shared. Wallet ids only need
+//! to be unique within their fleet, addresses across all fleets.
+//! [`Settings::fleet_settings`] gives the settings of each fleet as if it
+//! were configured alone.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
-use std::path::Path;
+use std::net::SocketAddr;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
 
-use alloy::primitives::Address;
+use alloy::primitives::{Address, U256};
+use chrono::{DateTime, TimeZone, Utc};
+use evm_provider::AssignmentStrategy;
+use fleet_core::plugins::{
+    ActionId, DEFAULT_PRIORITY, HealthSettings, Priority, SelectionStrategy,
+};
+use fleet_core::metrics::CorrelationSettings;
+use fleet_core::profiles::{BehaviorProfile, DiversityTargets, ProfileCatalog};
+use fleet_core::safety::{BudgetCaps, DegradationSettings, SpendLimit};
+use fleet_core::scheduler::RampSettings;
+use fleet_core::sensitive::Sensitive;
+use fleet_core::wallet::{RetirementSettings, WarmupSettings};
+use ghostnet_actions::{DeathRateTable, GhostnetConfig};
+use fleet_core::validation::ConfigReport;
+use ghostnet_actions::config::{
+    DeadPoolSettings, ExtractStrategy, GameFilter, GasSettings, StreakProtection,
+};
 use serde::{Deserialize, Serialize};
 use tracing::debug;
 
 use crate::error::{ConfigError, Result};
 
+/// Plugins the service can run.
+pub const PLUGINS: [&str; 1] = ["ghostnet"];
+
+/// Why a private key in the config file is warned about.
+const RAW_KEY_WARNING: &str =
+    "raw private keys are for local development; use a keystore or mnemonic";
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // SETTINGS
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -42,9 +115,22 @@ pub struct Settings {
     #[serde(default)]
     pub service: ServiceConfig,
 
-    /// Chain/network configuration.
+    /// Chain the fleet runs on.
+    ///
+    /// Either given directly as `[chain]`, or the profile of `chains` chosen
+    /// by [`select_chain`](Self::select_chain).
+    #[serde(default)]
     pub chain: ChainConfig,
 
+    /// Named chain profiles.
+    #[serde(default)]
+    pub chains: HashMap<String, ChainConfig>,
+
+    /// Name of the selected chain profile, set by
+    /// [`select_chain`](Self::select_chain).
+    #[serde(skip)]
+    pub selected_chain: Option<String>,
+
     /// Wallet configurations.
     #[serde(default)]
     pub wallets: Vec<WalletConfig>,
@@ -60,6 +146,67 @@ pub struct Settings {
     /// Behavior profile definitions.
     #[serde(default)]
     pub profiles: HashMap<String, ProfileConfig>,
+
+    /// Wallet group limits, by group name.
+    #[serde(default)]
+    pub groups: HashMap<String, GroupConfig>,
+
+    /// Seed phrases that wallet keys are derived from, by name.
+    #[serde(default)]
+    pub mnemonics: HashMap<String, MnemonicConfig>,
+
+    /// Simulation settings (used with `--simulate`).
+    #[serde(default)]
+    pub simulation: SimulationConfig,
+
+    /// Control socket settings (used by `ghost-fleet ctl`).
+    #[serde(default)]
+    pub control: ControlConfig,
+
+    /// Active-passive leadership among several processes.
+    #[serde(default)]
+    pub leader: LeaderConfig,
+
+    /// Warm-up of new wallets.
+    #[serde(default)]
+    pub warmup: WarmupConfig,
+
+    /// Ramp-up of wallets overdue at startup.
+    #[serde(default)]
+    pub cold_start: ColdStartConfig,
+
+    /// Fleet-level diversity of profiles and lockstep detection.
+    #[serde(default)]
+    pub diversity: DiversityConfig,
+
+    /// Operator review of planned actions.
+    #[serde(default)]
+    pub review: ReviewConfig,
+
+    /// Winding down of retiring wallets.
+    #[serde(default)]
+    pub retirement: RetirementConfig,
+
+    /// Daily wallet activity reports.
+    #[serde(default)]
+    pub report: ReportConfig,
+
+    /// Early wakeups of wallets on protocol events.
+    #[serde(default)]
+    pub triggers: TriggersConfig,
+
+    /// Read-only and paused tiers while the RPC is partly available.
+    #[serde(default)]
+    pub degradation: DegradationConfig,
+
+    /// Named fleets run side by side, replacing the top-level `wallets`.
+    #[serde(default, rename = "fleet")]
+    pub fleets: BTreeMap<String, FleetConfig>,
+
+    /// Issues found in the file itself by [`load`](Self::load), such as
+    /// addresses with a wrong checksum.
+    #[serde(skip)]
+    pub load_issues: ConfigReport,
 }
 
 impl Settings {
@@ -74,104 +221,616 @@ impl Settings {
                 source: e,
             })?;
 
-        let settings: Self = toml::from_str(&content)
+        let mut settings: Self = toml::from_str(&content)
             .map_err(|e| ConfigError::Parse {
                 path: path.to_path_buf(),
                 source: e,
             })?;
 
+        // Addresses are parsed regardless of case, so a typo in a checksummed
+        // address would go unnoticed
+        if let Ok(table) = content.parse::<toml::Table>() {
+            check_checksums(&toml::Value::Table(table), "", &mut settings.load_issues);
+        }
+
         Ok(settings)
     }
 
-    /// Validate the configuration.
-    pub fn validate(&self) -> Result<()> {
-        // Check chain configuration
+    /// Make a chain profile the active `chain` and load its deployment
+    /// manifest.
+    ///
+    /// The profile is `name` if given, otherwise `service.default_chain`.
+    /// Without any `[chains]` the `[chain]` table stays active.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no profile is selected or the selected one does not
+    /// exist, if both `[chain]` and `[chains]` are configured, or if the
+    /// manifest cannot be read or is for another chain.
+    pub fn select_chain(&mut self, name: Option<&str>) -> Result<()> {
+        if self.chains.is_empty() {
+            if let Some(name) = name {
+                return Err(ConfigError::Validation(format!(
+                    "Chain '{name}' selected but no [chains] are configured"
+                ))
+                .into());
+            }
+            return self.chain.load_addresses_file("chain");
+        }
+
+        if !self.chain.rpc_url.is_empty() {
+            return Err(ConfigError::Validation(
+                "Configure either [chain] or [chains.<name>], not both".into(),
+            )
+            .into());
+        }
+
+        let mut available: Vec<_> = self.chains.keys().map(String::as_str).collect();
+        available.sort_unstable();
+        let available = available.join(", ");
+
+        let Some(name) = name.or(self.service.default_chain.as_deref()) else {
+            return Err(ConfigError::Validation(format!(
+                "No chain selected: pass --chain or set service.default_chain \
+                 (available: {available})"
+            ))
+            .into());
+        };
+        let Some(chain) = self.chains.get(name) else {
+            return Err(ConfigError::Validation(format!(
+                "Chain '{name}' not found in [chains] (available: {available})"
+            ))
+            .into());
+        };
+
+        let mut chain = chain.clone();
+        chain.load_addresses_file(&format!("chains.{name}"))?;
+        self.chain = chain;
+        self.selected_chain = Some(name.to_string());
+        Ok(())
+    }
+
+    /// Config key of the active chain, for messages.
+    #[must_use]
+    pub fn chain_key(&self) -> String {
+        self.selected_chain
+            .as_ref()
+            .map_or_else(|| "chain".into(), |name| format!("chains.{name}"))
+    }
+
+    /// GHOSTNET plugin configuration for the active chain.
+    ///
+    /// Returns `None` if the plugin is not configured or the chain lacks one
+    /// of the contract addresses.
+    #[must_use]
+    pub fn ghostnet_config(&self) -> Option<GhostnetConfig> {
+        let plugin = self.plugins.ghostnet.as_ref()?;
+        self.chain
+            .ghostnet
+            .missing()
+            .is_empty()
+            .then(|| self.build_ghostnet_config(plugin))
+    }
+
+    /// GHOSTNET plugin configuration of `plugin` for the active chain, with
+    /// the zero address for contracts the chain lacks.
+    fn build_ghostnet_config(&self, plugin: &GhostnetPluginConfig) -> GhostnetConfig {
+        let addresses = &self.chain.ghostnet;
+        let mut config = GhostnetConfig::new(
+            addresses.ghost_core.unwrap_or_default(),
+            addresses.hash_crash.unwrap_or_default(),
+            addresses.arcade_core.unwrap_or_default(),
+            addresses.data_token.unwrap_or_default(),
+            self.chain.chain_id,
+        );
+        config.dead_pool = addresses.dead_pool;
+        config.behavior.max_balance_age_secs = plugin.max_balance_age_secs;
+        config.behavior.hashcrash_loss_streak = plugin.hashcrash_loss_streak;
+        config.behavior.hashcrash_loss_streak_bet_pct = plugin.hashcrash_loss_streak_bet_pct;
+        config.behavior.death_reduction_boosts = plugin.death_reduction_boosts;
+        config.behavior.yield_multiplier_boosts = plugin.yield_multiplier_boosts;
+        config.behavior.max_boost_reward_share = plugin.max_boost_reward_share;
+        config.behavior.death_rates = plugin.death_rates;
+        config.behavior.min_extract_edge_bps = plugin.min_extract_edge_bps;
+        config.behavior.imminent_cull_risk_bps = plugin.imminent_cull_risk_bps;
+        config.behavior.default_exit_toll_bps = plugin.default_exit_toll_bps;
+        config.behavior.exit_toll_ttl_secs = plugin.exit_toll_ttl_secs;
+        config.behavior.reset_danger_secs = plugin.reset_danger_secs;
+        config.behavior.reset_timer_ttl_secs = plugin.reset_timer_ttl_secs;
+        config.behavior.transfer_tax_ttl_secs = plugin.transfer_tax_ttl_secs;
+        config.behavior.extract_strategy = plugin.extract_strategy;
+        config.behavior.approve_before_stake = plugin.approve_before_stake;
+        config.behavior.reentry_probability = plugin.reentry_probability;
+        config.behavior.streak_protection = plugin.streak_protection.clone();
+        config.behavior.plays_arcade = plugin.arcade_enabled;
+        config.arcade_games = plugin.arcade_games.clone();
+        config.behavior.plays_dead_pool = plugin.dead_pool_enabled;
+        config.behavior.dead_pool = plugin.dead_pool.clone();
+        config.gas = plugin.gas.clone();
+        if let Ok(spend) = plugin.max_boost_spend.parse() {
+            config.behavior.max_boost_spend = spend;
+        }
+        if let Ok(reserve) = plugin.data_reserve.parse() {
+            config.behavior.data_reserve = reserve;
+        }
+        if let Ok(min) = plugin.min_net_extract.parse() {
+            config.behavior.min_net_extract = min;
+        }
+        if let Ok(cost) = plugin.extract_gas_cost.parse() {
+            config.behavior.extract_gas_cost = cost;
+        }
+        config
+    }
+
+    /// Check the chain ID reported by the provider against the active chain.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the RPC endpoint serves another chain.
+    pub fn check_chain_id(&self, reported: u64) -> Result<()> {
+        if reported == self.chain.chain_id {
+            return Ok(());
+        }
+        Err(ConfigError::ChainIdMismatch {
+            chain: self.chain_key(),
+            expected: self.chain.chain_id,
+            actual: reported,
+        }
+        .into())
+    }
+
+    /// Settings of each `[fleet.<name>]`, by name.
+    ///
+    /// A fleet's settings are these with its wallets, its profiles added to
+    /// (and replacing those of the same name in) `[profiles]`, and its plugin
+    /// configuration if it has one. Empty without fleets.
+    #[must_use]
+    pub fn fleet_settings(&self) -> Vec<(String, Self)> {
+        self.fleets
+            .iter()
+            .map(|(name, fleet)| {
+                let mut settings = Self {
+                    wallets: fleet.wallets.clone(),
+                    fleets: BTreeMap::new(),
+                    ..self.clone()
+                };
+                settings.profiles.extend(fleet.profiles.clone());
+                if let Some(plugins) = &fleet.plugins {
+                    settings.plugins = plugins.clone();
+                }
+                (name.clone(), settings)
+            })
+            .collect()
+    }
+
+    /// Check the configuration, collecting every issue instead of stopping
+    /// at the first.
+    ///
+    /// Issues name their key by its path in the config file, e.g.
+    /// `wallets[3].key_source.mnemonic`. The chain is named by
+    /// [`chain_key`](Self::chain_key); with fleets, wallets, fleet profiles
+    /// and fleet plugins are under `fleet.<name>`.
+    #[must_use]
+    pub fn check(&self) -> ConfigReport {
+        let mut report = self.load_issues.clone();
+
+        // Chain configuration
+        let chain = self.chain_key();
         if self.chain.rpc_url.is_empty() {
-            return Err(ConfigError::Validation("chain.rpc_url is required".into()).into());
+            report.error(format!("{chain}.rpc_url"), "is required");
+        }
+        if self.chain.chain_id == 0 {
+            report.error(format!("{chain}.chain_id"), "must be > 0");
+        }
+        report.extend_under(&chain, self.chain.check_endpoints());
+
+        // Group limits
+        let mut groups: Vec<_> = self.groups.iter().collect();
+        groups.sort_unstable_by_key(|(name, _)| *name);
+        for (name, group) in groups {
+            if group.max_actions == 0 {
+                report.error(format!("groups.{name}.max_actions"), "must be > 0");
+            }
+            if group.window_secs == 0 {
+                report.error(format!("groups.{name}.window_secs"), "must be > 0");
+            }
+        }
+
+        // Service-wide sections
+        report.extend_under("service", self.service.check());
+        report.extend_under("simulation", self.simulation.check());
+        report.extend_under("safety", self.safety.check());
+        report.extend_under("safety.budget", self.safety.budget.check());
+        report.extend_under("control", self.control.check());
+        report.extend_under("leader", self.leader.check());
+        if self.leader.enabled && self.service.state_file.is_none() {
+            report.error(
+                "service.state_file",
+                "is required with leader enabled, for a standby to take over",
+            );
+        }
+        if self.leader.enabled && !self.fleets.is_empty() {
+            report.error("leader.enabled", "is not supported with [fleet.<name>] yet");
         }
+        report.extend_under("warmup", self.warmup.check());
+        report.extend_under("cold_start", self.cold_start.check());
+        report.extend_under("diversity", self.diversity.check());
+        report.extend_under("review", self.review.check());
+        report.extend_under("retirement", self.retirement.check());
+        report.extend_under("report", self.report.check());
+        report.extend_under("triggers", self.triggers.check());
+        report.extend_under("degradation", self.degradation.check());
+        if self.triggers.enabled && !self.fleets.is_empty() {
+            report.error(
+                "triggers.enabled",
+                "is not supported with [fleet.<name>] yet",
+            );
+        }
+        if self.budgets().all(BudgetConfig::is_unlimited) {
+            report.warning("safety.budget", "no budget caps are set; spending is unlimited");
+        }
+
+        report.extend(self.resolve_profiles(self.profiles.keys()).1);
+        report.extend(self.check_plugins("plugins"));
+        if self.fleets.is_empty() {
+            report.extend_under("wallets", self.check_wallets());
+        } else {
+            self.check_fleets(&mut report);
+        }
+        report
+    }
+
+    /// Validate the configuration.
+    ///
+    /// Returns the warnings of [`check`](Self::check).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigError::Invalid`] with every issue if any is an error.
+    pub fn validate(&self) -> Result<ConfigReport> {
+        let report = self.check();
+        if report.has_errors() {
+            return Err(ConfigError::Invalid(report).into());
+        }
+        Ok(report)
+    }
+
+    /// Every budget of the config: the fleet's, and those of all profiles
+    /// and wallets.
+    fn budgets(&self) -> impl Iterator<Item = &BudgetConfig> {
+        let fleets = self.fleets.values();
+        std::iter::once(&self.safety.budget)
+            .chain(self.profiles.values().map(|profile| &profile.budget))
+            .chain(self.wallets.iter().map(|wallet| &wallet.budget))
+            .chain(fleets.clone().flat_map(|fleet| fleet.profiles.values()).map(|p| &p.budget))
+            .chain(fleets.flat_map(|fleet| &fleet.wallets).map(|wallet| &wallet.budget))
+    }
 
-        // Check that enabled plugins have configuration
-        for plugin_id in &self.plugins.enabled {
-            if plugin_id == "ghostnet" && self.plugins.ghostnet.is_none() {
-                return Err(ConfigError::Validation(
-                    "Plugin 'ghostnet' is enabled but [plugins.ghostnet] is not configured".into(),
-                ).into());
+    /// Check each fleet as if it were configured alone.
+    fn check_fleets(&self, report: &mut ConfigReport) {
+        if !self.wallets.is_empty() {
+            report.error("wallets", "configure wallets either here or in [fleet.<name>], not both");
+        }
+        // Fleets share the endpoint pool, which tracks wallets by address
+        let mut owners: HashMap<Address, &str> = HashMap::new();
+        for (name, fleet) in &self.fleets {
+            let key = format!("fleet.{name}");
+            if name.is_empty() || name.contains('/') {
+                report.error(&key, "fleet names must be non-empty and must not contain '/'");
+            }
+            for (i, wallet) in fleet.wallets.iter().enumerate() {
+                let owner = *owners.entry(wallet.address).or_insert(name);
+                if owner != name {
+                    report.error(
+                        format!("{key}.wallets[{i}].address"),
+                        format!("is also a wallet of fleet '{owner}'"),
+                    );
+                }
+            }
+        }
+        for (name, settings) in self.fleet_settings() {
+            let key = format!("fleet.{name}");
+            let fleet = &self.fleets[&name];
+            report.extend_under(&format!("{key}.wallets"), settings.check_wallets());
+            report.extend_under(&key, settings.resolve_profiles(fleet.profiles.keys()).1);
+            if fleet.plugins.is_some() {
+                report.extend(settings.check_plugins(&format!("{key}.plugins")));
             }
         }
+    }
 
-        // Check wallet configurations
+    /// Check `[[wallets]]`, with paths relative to `wallets`.
+    fn check_wallets(&self) -> ConfigReport {
+        let mut report = ConfigReport::new();
+        let mut ids = HashSet::new();
+        let mut addresses = HashMap::new();
         for (i, wallet) in self.wallets.iter().enumerate() {
+            let mut issues = ConfigReport::new();
             if wallet.id.is_empty() {
-                return Err(ConfigError::Validation(
-                    format!("wallets[{i}].id is required"),
-                ).into());
+                issues.error("id", "is required");
+            } else if !ids.insert(&wallet.id) {
+                issues.error("id", format!("'{}' is used twice", wallet.id));
+            }
+            let first = *addresses.entry(wallet.address).or_insert(i);
+            if first != i {
+                issues.error("address", format!("is also the address of wallets[{first}]"));
             }
             if wallet.profile.is_empty() {
-                return Err(ConfigError::Validation(
-                    format!("wallets[{i}].profile is required"),
-                ).into());
-            }
-            // Check profile exists
-            if !self.profiles.contains_key(&wallet.profile) {
-                let profile = &wallet.profile;
-                return Err(ConfigError::Validation(
-                    format!(
-                        "wallets[{i}].profile '{profile}' not found in [profiles]"
-                    ),
-                ).into());
+                issues.error("profile", "is required");
+            } else if !self.profiles.contains_key(&wallet.profile) {
+                issues.error("profile", format!("'{}' not found in [profiles]", wallet.profile));
+            }
+            self.check_key_source(wallet, &mut issues);
+            if wallet.retiring && wallet.standby {
+                issues.error("standby", "a wallet cannot be both retiring and a standby");
             }
+            if wallet.sweep_to == Some(wallet.address) {
+                issues.error("sweep_to", "must not be the wallet's own address");
+            }
+            issues.extend_under("budget", wallet.budget.check());
+            report.extend_under(&format!("[{i}]"), issues);
         }
+        report
+    }
 
-        // Check safety settings
-        if self.safety.max_consecutive_errors == 0 {
-            return Err(ConfigError::Validation(
-                "safety.max_consecutive_errors must be > 0".into(),
-            ).into());
+    /// Check the key source of a wallet.
+    fn check_key_source(&self, wallet: &WalletConfig, issues: &mut ConfigReport) {
+        if wallet.key_source.is_some() && wallet.private_key.is_some() {
+            issues.error("private_key", "must not be set along with key_source");
+        }
+        match &wallet.key_source {
+            Some(KeySource::Keystore { path, .. }) if path.as_os_str().is_empty() => {
+                issues.error("key_source.path", "is required");
+            }
+            Some(KeySource::Mnemonic { mnemonic, .. })
+                if !self.mnemonics.contains_key(mnemonic) =>
+            {
+                issues.error(
+                    "key_source.mnemonic",
+                    format!("'{mnemonic}' not found in [mnemonics]"),
+                );
+            }
+            Some(KeySource::Remote { url, .. })
+                if !(url.starts_with("http://") || url.starts_with("https://")) =>
+            {
+                issues.error("key_source.url", "must be an http:// or https:// URL");
+            }
+            Some(KeySource::Remote {
+                timeout_secs: 0, ..
+            }) => {
+                issues.error("key_source.timeout_secs", "must be > 0");
+            }
+            Some(KeySource::Raw { .. }) => {
+                issues.warning("key_source", RAW_KEY_WARNING);
+            }
+            _ if wallet.private_key.is_some() => {
+                issues.warning("private_key", RAW_KEY_WARNING);
+            }
+            _ => {}
         }
+    }
 
-        // Validate profile bounds
-        for (name, profile) in &self.profiles {
-            // All probability/factor values must be 0.0..=1.0
-            if !(0.0..=1.0).contains(&profile.risk_tolerance) {
-                return Err(ConfigError::Validation(
-                    format!("profiles[{name}].risk_tolerance must be between 0.0 and 1.0"),
-                ).into());
+    /// Check `[plugins]`, found at `key` (`plugins`, or the plugins of a
+    /// fleet).
+    ///
+    /// Issues of the GHOSTNET contract addresses are reported at the chain.
+    fn check_plugins(&self, key: &str) -> ConfigReport {
+        let mut report = ConfigReport::new();
+        let plugins = &self.plugins;
+        for (i, plugin_id) in plugins.enabled.iter().enumerate() {
+            if !PLUGINS.contains(&plugin_id.as_str()) {
+                report.warning(
+                    format!("{key}.enabled[{i}]"),
+                    format!(
+                        "'{plugin_id}' is not a known plugin ({}) and is not run",
+                        PLUGINS.join(", ")
+                    ),
+                );
             }
-            if !(0.0..=1.0).contains(&profile.patience) {
-                return Err(ConfigError::Validation(
-                    format!("profiles[{name}].patience must be between 0.0 and 1.0"),
-                ).into());
+        }
+        let mut priorities: Vec<_> = plugins.priorities.keys().collect();
+        priorities.sort_unstable();
+        for plugin_id in priorities {
+            if !plugins.enabled.contains(plugin_id) {
+                report.warning(
+                    format!("{key}.priorities.{plugin_id}"),
+                    format!("has no effect: plugin '{plugin_id}' is not enabled"),
+                );
             }
-            if !(0.0..=1.0).contains(&profile.off_hours_factor) {
-                return Err(ConfigError::Validation(
-                    format!("profiles[{name}].off_hours_factor must be between 0.0 and 1.0"),
-                ).into());
+        }
+        report.extend_under(&format!("{key}.health"), plugins.health.check());
+
+        let enabled = plugins.enabled.iter().any(|id| id == "ghostnet");
+        let Some(plugin) = &plugins.ghostnet else {
+            if enabled {
+                report.error(format!("{key}.ghostnet"), "is required when 'ghostnet' is enabled");
             }
-            if !(0.0..=1.0).contains(&profile.afk_probability) {
-                return Err(ConfigError::Validation(
-                    format!("profiles[{name}].afk_probability must be between 0.0 and 1.0"),
-                ).into());
+            return report;
+        };
+        let chain = self.chain_key();
+        let missing = self.chain.ghostnet.missing();
+        if enabled {
+            for address in &missing {
+                report.error(
+                    format!("{chain}.ghostnet.{address}"),
+                    "is required when plugin 'ghostnet' is enabled",
+                );
             }
-            // Hour values must be < 24
-            if profile.active_hours_start >= 24 {
-                return Err(ConfigError::Validation(
-                    format!("profiles[{name}].active_hours_start must be < 24"),
-                ).into());
+        }
+        report.extend_under(&format!("{key}.ghostnet"), plugin.check());
+
+        // The plugin's own checks, with its keys mapped back to the file
+        for mut issue in self.build_ghostnet_config(plugin).validate() {
+            let (section, rest) = issue.path.split_once('.').unwrap_or((&issue.path, ""));
+            issue.path = match section {
+                "chain_id" => continue,
+                "behavior" => format!("{key}.ghostnet.{rest}"),
+                "gas" | "arcade_games" => format!("{key}.ghostnet.{}", issue.path),
+                address if missing.contains(&address) => continue,
+                address => format!("{chain}.ghostnet.{address}"),
+            };
+            report.extend([issue]);
+        }
+        report
+    }
+
+    /// Resolve the profiles `names` of `[profiles]`, with a report of what is
+    /// wrong with them.
+    ///
+    /// Profiles that fail to resolve are left out of the catalog.
+    fn resolve_profiles<'a>(
+        &self,
+        names: impl IntoIterator<Item = &'a String>,
+    ) -> (ProfileCatalog, ConfigReport) {
+        let mut names: Vec<&String> = names.into_iter().collect();
+        names.sort();
+
+        let mut catalog = ProfileCatalog::new();
+        let mut report = ConfigReport::new();
+        for name in names {
+            let key = format!("profiles.{name}");
+            report.extend_under(&format!("{key}.budget"), self.profiles[name].budget.check());
+            let Some(profile) = self.resolve_profile(name, &mut Vec::new(), &mut report) else {
+                continue;
+            };
+            for error in profile.validate() {
+                report.error(format!("{key}.{}", error.field()), error.to_string());
             }
-            if profile.active_hours_end >= 24 {
-                return Err(ConfigError::Validation(
-                    format!("profiles[{name}].active_hours_end must be < 24"),
-                ).into());
+            catalog.insert(profile);
+        }
+        (catalog, report)
+    }
+
+    /// Resolve `[profiles]` into concrete behavior profiles.
+    ///
+    /// Fields a profile does not set come from the profile it `extends`
+    /// (another profile, or a preset if there is no profile of that name or
+    /// a profile extends the preset it is named after), or from
+    /// [`BehaviorProfile::new`] without `extends`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigError::Invalid`] naming each profile that extends an
+    /// unknown profile, whose `extends` chain is circular, or with a field
+    /// out of bounds.
+    pub fn profile_catalog(&self) -> Result<ProfileCatalog> {
+        let (catalog, report) = self.resolve_profiles(self.profiles.keys());
+        if report.has_errors() {
+            return Err(ConfigError::Invalid(report).into());
+        }
+        Ok(catalog)
+    }
+
+    /// Resolve `profiles[name]`, with `chain` the profiles extending it.
+    ///
+    /// Returns `None` after recording why in `report` if it does not
+    /// resolve.
+    fn resolve_profile(
+        &self,
+        name: &str,
+        chain: &mut Vec<String>,
+        report: &mut ConfigReport,
+    ) -> Option<BehaviorProfile> {
+        if chain.iter().any(|extending| extending == name) {
+            chain.push(name.to_string());
+            report.error(
+                format!("profiles.{}.extends", chain[0]),
+                format!("is circular: {}", chain.join(" -> ")),
+            );
+            return None;
+        }
+        let config = &self.profiles[name];
+
+        let base = match config.extends.as_deref() {
+            None => BehaviorProfile::new(name),
+            Some(parent) if parent != name && self.profiles.contains_key(parent) => {
+                chain.push(name.to_string());
+                let base = self.resolve_profile(parent, chain, report)?;
+                chain.pop();
+                base
             }
-            // AFK hours: min <= max
-            if profile.afk_min_hours > profile.afk_max_hours {
-                return Err(ConfigError::Validation(
-                    format!("profiles[{name}].afk_min_hours must be <= afk_max_hours"),
-                ).into());
+            Some(parent) => {
+                let Some(preset) = BehaviorProfile::preset(parent) else {
+                    report.error(
+                        format!("profiles.{name}.extends"),
+                        format!(
+                            "'{parent}' is neither a profile nor a preset ({})",
+                            BehaviorProfile::PRESETS.join(", ")
+                        ),
+                    );
+                    return None;
+                };
+                preset
             }
+        };
+
+        let mut profile = config.apply(base);
+        profile.name = name.to_string();
+        Some(profile)
+    }
+
+    /// Budget caps of a wallet: its own, falling back to its profile's.
+    #[must_use]
+    pub fn wallet_budget(&self, wallet: &WalletConfig) -> BudgetCaps {
+        wallet.budget.or(&self.profile_budget(&wallet.profile)).to_caps()
+    }
+
+    /// Budget caps of a profile: its own, falling back to those of the
+    /// profiles it extends.
+    fn profile_budget(&self, name: &str) -> BudgetConfig {
+        let mut budget = BudgetConfig::default();
+        let mut next = Some(name);
+        // Bounded, in case the chain is circular and unvalidated
+        for _ in 0..=self.profiles.len() {
+            let Some(profile) = next.and_then(|name| self.profiles.get(name)) else {
+                break;
+            };
+            budget = budget.or(&profile.budget);
+            next = profile.extends.as_deref().filter(|parent| Some(*parent) != next);
         }
+        budget
+    }
+}
 
-        Ok(())
+/// Record an error at `key` unless `amount` is an integer amount in wei.
+fn check_wei(report: &mut ConfigReport, key: &str, amount: &str) {
+    if amount.parse::<U256>().is_err() {
+        report.error(key, format!("is not an integer amount in wei: {amount}"));
+    }
+}
+
+/// Record each address string under `value` whose mixed case does not match
+/// its EIP-55 checksum, at its key path below `path`.
+///
+/// All-lowercase and all-uppercase addresses carry no checksum and pass.
+fn check_checksums(value: &toml::Value, path: &str, report: &mut ConfigReport) {
+    match value {
+        toml::Value::String(text) => {
+            let Some(hex) = text.strip_prefix("0x").filter(|hex| hex.len() == 40) else {
+                return;
+            };
+            let mixed = hex.chars().any(|c| c.is_ascii_lowercase())
+                && hex.chars().any(|c| c.is_ascii_uppercase());
+            let Ok(address) = text.parse::<Address>() else {
+                return;
+            };
+            let checksummed = address.to_checksum(None);
+            if mixed && checksummed != *text {
+                report.error(path, format!("checksum does not match; expected {checksummed}"));
+            }
+        }
+        toml::Value::Table(table) => {
+            for (key, value) in table {
+                let path = if path.is_empty() { key.clone() } else { format!("{path}.{key}") };
+                check_checksums(value, &path, report);
+            }
+        }
+        toml::Value::Array(values) => {
+            for (i, value) in values.iter().enumerate() {
+                check_checksums(value, &format!("{path}[{i}]"), report);
+            }
+        }
+        _ => {}
     }
 }
 
@@ -193,6 +852,24 @@ pub struct ServiceConfig {
     /// Health check HTTP port (0 to disable).
     #[serde(default)]
     pub health_port: u16,
+
+    /// Chain profile to run on when `--chain` is not given.
+    #[serde(default)]
+    pub default_chain: Option<String>,
+
+    /// File the wallets' schedules and circuit breakers are saved to, so
+    /// they survive restarts. Not saved if unset.
+    #[serde(default)]
+    pub state_file: Option<PathBuf>,
+
+    /// Seconds between saves of the state file, besides the save on shutdown.
+    #[serde(default = "default_state_save_interval")]
+    pub state_save_interval_secs: u64,
+
+    /// File the profit and loss ledger is appended to, so its totals
+    /// survive restarts (see [`crate::pnl`]). Kept in memory only if unset.
+    #[serde(default)]
+    pub pnl_file: Option<PathBuf>,
 }
 
 fn default_service_name() -> String {
@@ -203,107 +880,1601 @@ const fn default_tick_interval() -> u64 {
     1000
 }
 
+const fn default_state_save_interval() -> u64 {
+    60
+}
+
+impl ServiceConfig {
+    /// State file of the fleet named `fleet`, if any: the configured file
+    /// with the fleet's name added to its stem, so fleets run together do
+    /// not overwrite each other's state.
+    #[must_use]
+    pub fn state_file_of(&self, fleet: Option<&str>) -> Option<PathBuf> {
+        Some(file_of(self.state_file.as_ref()?, fleet))
+    }
+
+    /// PnL ledger file of the fleet named `fleet`, if any, named like its
+    /// [state file](Self::state_file_of).
+    #[must_use]
+    pub fn pnl_file_of(&self, fleet: Option<&str>) -> Option<PathBuf> {
+        Some(file_of(self.pnl_file.as_ref()?, fleet))
+    }
+
+    /// Check the state file settings.
+    fn check(&self) -> ConfigReport {
+        let mut report = ConfigReport::new();
+        if self.state_file.is_some() && self.state_save_interval_secs == 0 {
+            report.error("state_save_interval_secs", "must be > 0");
+        }
+        report
+    }
+}
+
 impl Default for ServiceConfig {
     fn default() -> Self {
         Self {
             name: default_service_name(),
             tick_interval_ms: default_tick_interval(),
             health_port: 0,
+            default_chain: None,
+            state_file: None,
+            state_save_interval_secs: default_state_save_interval(),
+            pnl_file: None,
         }
     }
 }
 
+/// `path` with the name of `fleet`, if one of several, added to its stem.
+fn file_of(path: &Path, fleet: Option<&str>) -> PathBuf {
+    let Some(fleet) = fleet else {
+        return path.to_path_buf();
+    };
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let name = path.extension().map_or_else(
+        || format!("{stem}.{fleet}"),
+        |ext| format!("{stem}.{fleet}.{}", ext.to_string_lossy()),
+    );
+    path.with_file_name(name)
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
-// CHAIN CONFIG
+// CONTROL CONFIG
 // ═══════════════════════════════════════════════════════════════════════════════
 
-/// Chain/network configuration.
+/// Settings of the control socket that `ghost-fleet ctl` talks to (see
+/// [`crate::control`]).
 #[derive(Debug, Clone, Deserialize, Serialize)]
-pub struct ChainConfig {
-    /// Chain ID.
-    pub chain_id: u64,
+pub struct ControlConfig {
+    /// Serve the control socket.
+    #[serde(default)]
+    pub enabled: bool,
 
-    /// RPC URL.
-    pub rpc_url: String,
+    /// Address to listen on.
+    #[serde(default = "default_control_listen")]
+    pub listen: String,
 
-    /// Chain type for provider selection.
-    #[serde(default = "default_chain_type")]
-    pub chain_type: String,
+    /// Shared token that requests must present. Required unless `listen` is
+    /// a loopback address.
+    #[serde(default)]
+    pub token: Option<String>,
+}
 
-    /// Gas limit override (for chains with unreliable estimation).
-    pub gas_limit_override: Option<u64>,
+fn default_control_listen() -> String {
+    "127.0.0.1:7878".into()
+}
 
-    /// Use realtime API if available (MegaETH).
-    #[serde(default)]
-    pub use_realtime: bool,
+impl Default for ControlConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            listen: default_control_listen(),
+            token: None,
+        }
+    }
 }
 
-fn default_chain_type() -> String {
-    "standard".into()
+impl ControlConfig {
+    /// Check the control socket settings.
+    fn check(&self) -> ConfigReport {
+        let mut report = ConfigReport::new();
+        if self.token.as_ref().is_some_and(String::is_empty) {
+            report.error("token", "must not be empty");
+        }
+        match self.listen.parse::<SocketAddr>() {
+            Err(_) => report.error("listen", format!("'{}' is not a socket address", self.listen)),
+            Ok(addr) if self.enabled && self.token.is_none() && !addr.ip().is_loopback() => {
+                report.error("token", "is required when listen is not a loopback address");
+            }
+            Ok(_) => {}
+        }
+        report
+    }
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
-// WALLET CONFIG
+// LEADER CONFIG
 // ═══════════════════════════════════════════════════════════════════════════════
 
-/// Individual wallet configuration.
+/// Which of several processes running the fleet acts (see
+/// [`crate::leader`]).
 #[derive(Debug, Clone, Deserialize, Serialize)]
-pub struct WalletConfig {
-    /// Unique wallet identifier.
-    pub id: String,
+pub struct LeaderConfig {
+    /// Act only while holding the fleet's lease, and stand by otherwise.
+    #[serde(default)]
+    pub enabled: bool,
 
-    /// Wallet address.
-    pub address: Address,
+    /// Name of this process among those sharing the lease. Defaults to
+    /// `ghost-fleet-<pid>`.
+    #[serde(default)]
+    pub node_id: Option<String>,
 
-    /// Behavior profile name.
-    pub profile: String,
+    /// File the lease is kept in, shared by the processes.
+    #[serde(default)]
+    pub lease_file: Option<PathBuf>,
 
-    /// Private key (hex, with or without 0x prefix).
-    /// In production, use encrypted keyfile instead.
-    pub private_key: Option<String>,
+    /// Seconds a lease lasts without renewal: the failover window.
+    #[serde(default = "default_lease_ttl_secs")]
+    pub lease_ttl_secs: u64,
 
-    /// Path to encrypted keyfile.
-    pub keyfile: Option<String>,
+    /// Seconds between renewals of the lease, and attempts to take it.
+    #[serde(default = "default_heartbeat_interval_secs")]
+    pub heartbeat_interval_secs: u64,
 
-    /// Whether this wallet is enabled.
-    #[serde(default = "default_true")]
-    pub enabled: bool,
+    /// Refresh the wallets' state from the chain each time a standby loads
+    /// the leader's saved state.
+    #[serde(default)]
+    pub standby_refresh: bool,
 }
 
-const fn default_true() -> bool {
-    true
+const fn default_lease_ttl_secs() -> u64 {
+    30
 }
 
-// ═══════════════════════════════════════════════════════════════════════════════
-// PLUGINS CONFIG
-// ═══════════════════════════════════════════════════════════════════════════════
-
-/// Plugin configuration.
-#[derive(Debug, Clone, Default, Deserialize, Serialize)]
-pub struct PluginsConfig {
-    /// List of enabled plugin IDs.
-    #[serde(default)]
-    pub enabled: Vec<String>,
-
-    /// GHOSTNET plugin configuration.
-    pub ghostnet: Option<GhostnetPluginConfig>,
+const fn default_heartbeat_interval_secs() -> u64 {
+    10
 }
 
-/// GHOSTNET-specific plugin configuration.
-#[derive(Debug, Clone, Deserialize, Serialize)]
-pub struct GhostnetPluginConfig {
-    /// GhostCore contract address.
-    pub ghost_core: Address,
+impl Default for LeaderConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            node_id: None,
+            lease_file: None,
+            lease_ttl_secs: default_lease_ttl_secs(),
+            heartbeat_interval_secs: default_heartbeat_interval_secs(),
+            standby_refresh: false,
+        }
+    }
+}
+
+impl LeaderConfig {
+    /// Name of this process among those sharing the lease.
+    #[must_use]
+    pub fn node_id(&self) -> String {
+        self.node_id
+            .clone()
+            .unwrap_or_else(|| format!("ghost-fleet-{}", std::process::id()))
+    }
+
+    /// Check the leadership settings.
+    fn check(&self) -> ConfigReport {
+        let mut report = ConfigReport::new();
+        if self.node_id.as_ref().is_some_and(String::is_empty) {
+            report.error("node_id", "must not be empty");
+        }
+        if self.enabled && self.lease_file.is_none() {
+            report.error("lease_file", "is required when enabled");
+        }
+        if self.heartbeat_interval_secs == 0 {
+            report.error("heartbeat_interval_secs", "must be > 0");
+        } else if self.heartbeat_interval_secs >= self.lease_ttl_secs {
+            report.error("heartbeat_interval_secs", "must be below lease_ttl_secs");
+        }
+        report
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// WARMUP CONFIG
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// How new wallets warm up (see [`WarmupPlan`](fleet_core::wallet::WarmupPlan)).
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct WarmupConfig {
+    /// Warm up wallets that have not acted yet.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Shortest warm-up, in days.
+    #[serde(default = "default_warmup_min_days")]
+    pub min_days: u32,
+
+    /// Longest warm-up, in days.
+    #[serde(default = "default_warmup_max_days")]
+    pub max_days: u32,
+
+    /// Share of their usual amounts that wallets start with.
+    #[serde(default = "default_warmup_initial_factor")]
+    pub initial_factor: f64,
+}
+
+fn default_warmup_min_days() -> u32 {
+    WarmupSettings::default().min_days
+}
+
+fn default_warmup_max_days() -> u32 {
+    WarmupSettings::default().max_days
+}
+
+fn default_warmup_initial_factor() -> f64 {
+    WarmupSettings::default().initial_factor
+}
+
+impl Default for WarmupConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_days: default_warmup_min_days(),
+            max_days: default_warmup_max_days(),
+            initial_factor: default_warmup_initial_factor(),
+        }
+    }
+}
+
+impl WarmupConfig {
+    /// Convert to the settings that plans are generated from.
+    #[must_use]
+    pub const fn to_settings(&self) -> WarmupSettings {
+        WarmupSettings {
+            enabled: self.enabled,
+            min_days: self.min_days,
+            max_days: self.max_days,
+            initial_factor: self.initial_factor,
+        }
+    }
+
+    /// Check the warm-up settings.
+    fn check(&self) -> ConfigReport {
+        let mut report = ConfigReport::new();
+        if self.min_days == 0 || self.min_days > self.max_days {
+            report.error("min_days", "must be > 0 and at most max_days");
+        }
+        if !(self.initial_factor > 0.0 && self.initial_factor <= 1.0) {
+            report.error("initial_factor", "must be > 0.0 and at most 1.0");
+        }
+        report
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// COLD START CONFIG
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// How wallets that are overdue at startup ramp up (see
+/// [`ColdStartRamp`](fleet_core::scheduler::ColdStartRamp)).
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ColdStartConfig {
+    /// Spread the first actions of overdue wallets instead of running them
+    /// all at once.
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    /// Window the first actions are spread over, in seconds.
+    #[serde(default = "default_ramp_window_secs")]
+    pub ramp_window_secs: u64,
+
+    /// Most first actions started within a minute of the ramp.
+    #[serde(default = "default_ramp_max_actions_per_minute")]
+    pub max_actions_per_minute: u32,
+
+    /// State reads of the wallets' first cycle per second, 0 for no limit.
+    #[serde(default = "default_ramp_max_reads_per_sec")]
+    pub max_reads_per_sec: u32,
+}
+
+#[allow(clippy::cast_sign_loss)] // The default window is positive
+fn default_ramp_window_secs() -> u64 {
+    RampSettings::default().window.num_seconds() as u64
+}
+
+fn default_ramp_max_actions_per_minute() -> u32 {
+    RampSettings::default().max_per_minute
+}
+
+fn default_ramp_max_reads_per_sec() -> u32 {
+    RampSettings::default().reads_per_sec
+}
+
+impl Default for ColdStartConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ramp_window_secs: default_ramp_window_secs(),
+            max_actions_per_minute: default_ramp_max_actions_per_minute(),
+            max_reads_per_sec: default_ramp_max_reads_per_sec(),
+        }
+    }
+}
+
+impl ColdStartConfig {
+    /// Convert to the settings that ramps are planned with.
+    #[must_use]
+    pub fn to_settings(&self) -> RampSettings {
+        RampSettings {
+            window: i64::try_from(self.ramp_window_secs)
+                .map_or(chrono::Duration::MAX, chrono::Duration::seconds),
+            max_per_minute: self.max_actions_per_minute,
+            reads_per_sec: self.max_reads_per_sec,
+        }
+    }
+
+    /// Check the cold-start settings.
+    fn check(&self) -> ConfigReport {
+        let mut report = ConfigReport::new();
+        if !(60..=86_400).contains(&self.ramp_window_secs) {
+            report.error("ramp_window_secs", "must be between 60 and 86400");
+        }
+        if self.max_actions_per_minute == 0 {
+            report.error("max_actions_per_minute", "must be > 0");
+        }
+        report
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// DIVERSITY CONFIG
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Distribution the fleet's profiles should have together (see
+/// [`FleetComposer`](fleet_core::profiles::FleetComposer)), and how its
+/// realized activity is checked for lockstep (see
+/// [`ActivityCorrelation`](fleet_core::metrics::ActivityCorrelation)).
+///
+/// Shares are fractions of the fleet's wallets, 0.0-1.0.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DiversityConfig {
+    /// Fleet seed the composition is derived from.
+    #[serde(default)]
+    pub seed: u64,
+
+    /// Share of wallets that must be active in every hour of the day.
+    #[serde(default)]
+    pub min_hourly_coverage: f64,
+
+    /// Target shares of equal-width `risk_tolerance` buckets, lowest first,
+    /// relative to their sum. Empty for no target.
+    #[serde(default)]
+    pub risk_buckets: Vec<f64>,
+
+    /// Largest share of wallets with identical interval parameters.
+    #[serde(default = "default_max_shared_interval_fraction")]
+    pub max_shared_interval_fraction: f64,
+
+    /// Width of the buckets actions are counted in, in seconds.
+    #[serde(default = "default_correlation_bucket_secs")]
+    pub correlation_bucket_secs: u64,
+
+    /// How far back actions are checked, in seconds.
+    #[serde(default = "default_correlation_window_secs")]
+    pub correlation_window_secs: u64,
+
+    /// Share of the window's actions in one bucket above which the fleet is
+    /// flagged.
+    #[serde(default = "default_correlation_threshold")]
+    pub correlation_threshold: f64,
+
+    /// Fewest actions in the window before the fleet can be flagged.
+    #[serde(default = "default_correlation_min_actions")]
+    pub correlation_min_actions: usize,
+}
+
+fn default_max_shared_interval_fraction() -> f64 {
+    DiversityTargets::default().max_shared_interval_fraction
+}
+
+#[allow(clippy::cast_sign_loss)] // The default bucket is positive
+fn default_correlation_bucket_secs() -> u64 {
+    CorrelationSettings::default().bucket.num_seconds() as u64
+}
+
+#[allow(clippy::cast_sign_loss)] // The default window is positive
+fn default_correlation_window_secs() -> u64 {
+    CorrelationSettings::default().window.num_seconds() as u64
+}
+
+fn default_correlation_threshold() -> f64 {
+    CorrelationSettings::default().threshold
+}
+
+fn default_correlation_min_actions() -> usize {
+    CorrelationSettings::default().min_actions
+}
+
+impl Default for DiversityConfig {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            min_hourly_coverage: 0.0,
+            risk_buckets: Vec::new(),
+            max_shared_interval_fraction: default_max_shared_interval_fraction(),
+            correlation_bucket_secs: default_correlation_bucket_secs(),
+            correlation_window_secs: default_correlation_window_secs(),
+            correlation_threshold: default_correlation_threshold(),
+            correlation_min_actions: default_correlation_min_actions(),
+        }
+    }
+}
+
+impl DiversityConfig {
+    /// Convert to the targets the fleet is composed for.
+    #[must_use]
+    pub fn to_targets(&self) -> DiversityTargets {
+        DiversityTargets {
+            min_hourly_coverage: self.min_hourly_coverage,
+            risk_buckets: self.risk_buckets.clone(),
+            max_shared_interval_fraction: self.max_shared_interval_fraction,
+        }
+    }
+
+    /// Convert to the settings realized activity is checked with.
+    #[must_use]
+    pub fn to_correlation_settings(&self) -> CorrelationSettings {
+        let seconds = |secs: u64| {
+            i64::try_from(secs).map_or(chrono::Duration::MAX, chrono::Duration::seconds)
+        };
+        CorrelationSettings {
+            bucket: seconds(self.correlation_bucket_secs),
+            window: seconds(self.correlation_window_secs),
+            threshold: self.correlation_threshold,
+            min_actions: self.correlation_min_actions,
+        }
+    }
+
+    /// Check the diversity settings.
+    fn check(&self) -> ConfigReport {
+        let mut report = ConfigReport::new();
+        for (key, share) in [
+            ("min_hourly_coverage", self.min_hourly_coverage),
+            ("max_shared_interval_fraction", self.max_shared_interval_fraction),
+        ] {
+            if !(0.0..=1.0).contains(&share) {
+                report.error(key, "must be between 0.0 and 1.0");
+            }
+        }
+        if self.risk_buckets.iter().any(|share| share.is_nan() || *share < 0.0) {
+            report.error("risk_buckets", "shares must be >= 0.0");
+        } else if !self.risk_buckets.is_empty() && self.risk_buckets.iter().sum::<f64>() <= 0.0 {
+            report.error("risk_buckets", "at least one share must be > 0.0");
+        }
+        if self.correlation_bucket_secs == 0 {
+            report.error("correlation_bucket_secs", "must be > 0");
+        }
+        if self.correlation_window_secs < self.correlation_bucket_secs {
+            report.error("correlation_window_secs", "must be at least correlation_bucket_secs");
+        }
+        if !(self.correlation_threshold > 0.0 && self.correlation_threshold <= 1.0) {
+            report.error("correlation_threshold", "must be > 0.0 and at most 1.0");
+        }
+        report
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// RETIREMENT CONFIG
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// How retiring wallets wind down (see
+/// [`Retirement`](fleet_core::wallet::Retirement)).
+///
+/// Dust amounts are integer amounts in wei.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RetirementConfig {
+    /// Native balance a retiring wallet may keep and still be retired.
+    #[serde(default = "default_dust_native_wei")]
+    pub dust_native_wei: String,
+
+    /// DATA balance a retiring wallet may keep and still be retired.
+    #[serde(default = "default_dust_data_wei")]
+    pub dust_data_wei: String,
+
+    /// Replace each retired wallet with the first standby wallet left.
+    #[serde(default = "default_true")]
+    pub replace_with_standby: bool,
+}
+
+fn default_dust_native_wei() -> String {
+    RetirementSettings::default().dust_native.to_string()
+}
+
+fn default_dust_data_wei() -> String {
+    RetirementSettings::default().dust_token.to_string()
+}
+
+impl Default for RetirementConfig {
+    fn default() -> Self {
+        Self {
+            dust_native_wei: default_dust_native_wei(),
+            dust_data_wei: default_dust_data_wei(),
+            replace_with_standby: true,
+        }
+    }
+}
+
+impl RetirementConfig {
+    /// Convert to the settings that retiring wallets are checked against.
+    ///
+    /// Amounts are expected to have been validated; any that do not parse
+    /// keep the default.
+    #[must_use]
+    pub fn to_settings(&self) -> RetirementSettings {
+        let defaults = RetirementSettings::default();
+        RetirementSettings {
+            dust_native: self.dust_native_wei.parse().unwrap_or(defaults.dust_native),
+            dust_token: self.dust_data_wei.parse().unwrap_or(defaults.dust_token),
+        }
+    }
+
+    /// Validate the retirement settings.
+    ///
+    /// A native sweep leaves its own gas behind, so with no dust at all a
+    /// wallet that sweeps would never be retired.
+    fn check(&self) -> ConfigReport {
+        let mut report = ConfigReport::new();
+        for (key, amount) in [
+            ("dust_native_wei", &self.dust_native_wei),
+            ("dust_data_wei", &self.dust_data_wei),
+        ] {
+            check_wei(&mut report, key, amount);
+        }
+        if self.dust_native_wei.parse::<U256>().is_ok_and(|dust| dust.is_zero()) {
+            report.error("dust_native_wei", "must be > 0");
+        }
+        report
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// REVIEW CONFIG
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Whether decided actions wait for an operator (see [`crate::review`]).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewMode {
+    /// Actions run as soon as they are decided.
+    #[default]
+    Off,
+
+    /// Each tick's actions run `delay_secs` after they were planned, unless
+    /// rejected.
+    Delay,
+
+    /// Each tick's actions run only once approved.
+    Strict,
+}
+
+/// Operator review of the actions the fleet decides.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ReviewConfig {
+    /// Review mode.
+    #[serde(default)]
+    pub mode: ReviewMode,
+
+    /// Seconds a planned batch waits for a rejection in `delay` mode.
+    #[serde(default = "default_review_delay_secs")]
+    pub delay_secs: u64,
+
+    /// File planned batches and their review are appended to, one JSON
+    /// object per line.
+    #[serde(default)]
+    pub journal: Option<PathBuf>,
+
+    /// Journal the data of planned actions, which can hold what their
+    /// calldata encodes, rather than its hash.
+    #[serde(default)]
+    pub journal_action_data: bool,
+}
+
+const fn default_review_delay_secs() -> u64 {
+    300
+}
+
+impl Default for ReviewConfig {
+    fn default() -> Self {
+        Self {
+            mode: ReviewMode::Off,
+            delay_secs: default_review_delay_secs(),
+            journal: None,
+            journal_action_data: false,
+        }
+    }
+}
+
+impl ReviewConfig {
+    /// Check the review settings.
+    fn check(&self) -> ConfigReport {
+        let mut report = ConfigReport::new();
+        if self.mode == ReviewMode::Delay && self.delay_secs == 0 {
+            report.error("delay_secs", "must be > 0 in delay mode; use mode = \"off\" instead");
+        }
+        report
+    }
+}
+
+/// Daily wallet activity reports (see [`crate::report`]).
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ReportConfig {
+    /// Write a report once a day.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Directory the reports are written to.
+    #[serde(default = "default_report_dir")]
+    pub dir: PathBuf,
+
+    /// Hour of the day (UTC) each report is written at, closing the day.
+    #[serde(default)]
+    pub hour_utc: u32,
+
+    /// URL each report is also POSTed to as JSON.
+    #[serde(default)]
+    pub webhook_url: Option<String>,
+
+    /// Times a failed POST is retried.
+    #[serde(default = "default_webhook_retries")]
+    pub webhook_retries: u32,
+
+    /// Delay before the first retry (milliseconds), doubling after each.
+    #[serde(default = "default_webhook_retry_delay_ms")]
+    pub webhook_retry_delay_ms: u64,
+
+    /// Seconds a POST may take.
+    #[serde(default = "default_webhook_timeout_secs")]
+    pub webhook_timeout_secs: u64,
+
+    /// Largest body POSTed; a larger report is sent without its wallet rows.
+    #[serde(default = "default_webhook_max_bytes")]
+    pub webhook_max_bytes: usize,
+}
+
+fn default_report_dir() -> PathBuf {
+    PathBuf::from("reports")
+}
+
+const fn default_webhook_retries() -> u32 {
+    3
+}
+
+const fn default_webhook_retry_delay_ms() -> u64 {
+    1000
+}
+
+const fn default_webhook_timeout_secs() -> u64 {
+    10
+}
+
+const fn default_webhook_max_bytes() -> usize {
+    256 * 1024
+}
+
+impl Default for ReportConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            dir: default_report_dir(),
+            hour_utc: 0,
+            webhook_url: None,
+            webhook_retries: default_webhook_retries(),
+            webhook_retry_delay_ms: default_webhook_retry_delay_ms(),
+            webhook_timeout_secs: default_webhook_timeout_secs(),
+            webhook_max_bytes: default_webhook_max_bytes(),
+        }
+    }
+}
+
+impl ReportConfig {
+    /// Check the report settings.
+    fn check(&self) -> ConfigReport {
+        let mut report = ConfigReport::new();
+        if self.hour_utc > 23 {
+            report.error("hour_utc", "must be an hour of the day, 0 to 23");
+        }
+        if let Some(url) = &self.webhook_url
+            && !(url.starts_with("http://") || url.starts_with("https://"))
+        {
+            report.error("webhook_url", "must be an http:// or https:// URL");
+        }
+        if self.webhook_timeout_secs == 0 {
+            report.error("webhook_timeout_secs", "must be > 0");
+        }
+        if self.webhook_max_bytes == 0 {
+            report.error("webhook_max_bytes", "must be > 0");
+        }
+        report
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TRIGGERS CONFIG
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Early wakeups of wallets on protocol events (see [`crate::trigger`]).
+///
+/// Events come from the indexer's event stream if `indexer_ws_url` is set,
+/// otherwise from polling the chain's logs. Each kind of event has a
+/// [`WakeRuleConfig`] of its own.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TriggersConfig {
+    /// Wake wallets early on protocol events.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// WebSocket URL of the indexer's event stream, e.g.
+    /// `ws://indexer:8080/api/v1/ws`.
+    #[serde(default)]
+    pub indexer_ws_url: Option<String>,
+
+    /// Seconds between two polls of the chain's logs, without an indexer.
+    #[serde(default = "default_trigger_poll_interval_secs")]
+    pub poll_interval_secs: u64,
+
+    /// TraceScan contract address, whose logs are polled for
+    /// `ScanExecuted` without an indexer.
+    #[serde(default, deserialize_with = "deserialize_address")]
+    pub trace_scan: Option<Address>,
+
+    /// DeadPool contract address, whose logs are polled for `RoundCreated`
+    /// without an indexer.
+    #[serde(default, deserialize_with = "deserialize_address")]
+    pub dead_pool: Option<Address>,
+
+    /// Actions that place a bet; `RoundCreated` wakes wallets that can
+    /// afford one of them.
+    #[serde(default = "default_bet_actions")]
+    pub bet_actions: Vec<String>,
+
+    /// Exit actions a woken wallet may run outside its active hours.
+    #[serde(default = "default_urgent_actions")]
+    pub urgent_actions: Vec<String>,
+
+    /// Wakeups on `ScanExecuted`, of the wallets with a position at the
+    /// scanned level.
+    #[serde(default)]
+    pub scan_executed: WakeRuleConfig,
+
+    /// Wakeups on `RoundCreated`, of the wallets that can bet.
+    #[serde(default = "default_round_created_rule")]
+    pub round_created: WakeRuleConfig,
+
+    /// Wakeups on `PositionCulled`, of the culled wallet.
+    #[serde(default)]
+    pub position_culled: WakeRuleConfig,
+}
+
+const fn default_trigger_poll_interval_secs() -> u64 {
+    5
+}
+
+fn default_bet_actions() -> Vec<String> {
+    vec![
+        "ghostnet.hashcrash_bet".to_string(),
+        "ghostnet.dead_pool_bet".to_string(),
+    ]
+}
+
+fn default_urgent_actions() -> Vec<String> {
+    vec!["ghostnet.extract".to_string()]
+}
+
+fn default_round_created_rule() -> WakeRuleConfig {
+    WakeRuleConfig {
+        sample_rate: 0.2,
+        ..WakeRuleConfig::default()
+    }
+}
+
+impl Default for TriggersConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            indexer_ws_url: None,
+            poll_interval_secs: default_trigger_poll_interval_secs(),
+            trace_scan: None,
+            dead_pool: None,
+            bet_actions: default_bet_actions(),
+            urgent_actions: default_urgent_actions(),
+            scan_executed: WakeRuleConfig::default(),
+            round_created: default_round_created_rule(),
+            position_culled: WakeRuleConfig::default(),
+        }
+    }
+}
+
+impl TriggersConfig {
+    /// Check the trigger settings.
+    fn check(&self) -> ConfigReport {
+        let mut report = ConfigReport::new();
+        if let Some(url) = &self.indexer_ws_url
+            && !(url.starts_with("ws://") || url.starts_with("wss://"))
+        {
+            report.error("indexer_ws_url", "must be a ws:// or wss:// URL");
+        }
+        if self.poll_interval_secs == 0 {
+            report.error("poll_interval_secs", "must be > 0");
+        }
+        for (key, actions) in [
+            ("bet_actions", &self.bet_actions),
+            ("urgent_actions", &self.urgent_actions),
+        ] {
+            for (i, action) in actions.iter().enumerate() {
+                if let Err(e) = ActionId::parse(action.as_str()) {
+                    report.error(format!("{key}[{i}]"), e.to_string());
+                }
+            }
+        }
+        if self.enabled && self.indexer_ws_url.is_none() {
+            for (key, address) in [
+                ("trace_scan", self.trace_scan),
+                ("dead_pool", self.dead_pool),
+            ] {
+                if address.is_none() {
+                    report.warning(key, "is not set, so no logs of it are polled");
+                }
+            }
+        }
+        report.extend_under("scan_executed", self.scan_executed.check());
+        report.extend_under("round_created", self.round_created.check());
+        report.extend_under("position_culled", self.position_culled.check());
+        report
+    }
+}
+
+/// Which of an event's target wallets wake, and how soon.
+///
+/// Each target wakes with probability `sample_rate`, at a delay drawn
+/// uniformly between `min_delay_secs` and `max_delay_secs` after the event.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub struct WakeRuleConfig {
+    /// Wake wallets on the event at all.
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    /// Share of the target wallets woken, 0.0-1.0.
+    #[serde(default = "default_wake_sample_rate")]
+    pub sample_rate: f64,
+
+    /// Least delay of a wakeup after the event, in seconds.
+    #[serde(default = "default_wake_min_delay_secs")]
+    pub min_delay_secs: u64,
+
+    /// Most delay of a wakeup after the event, in seconds.
+    #[serde(default = "default_wake_max_delay_secs")]
+    pub max_delay_secs: u64,
+}
+
+const fn default_wake_sample_rate() -> f64 {
+    1.0
+}
+
+const fn default_wake_min_delay_secs() -> u64 {
+    60
+}
+
+const fn default_wake_max_delay_secs() -> u64 {
+    600
+}
+
+impl Default for WakeRuleConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            sample_rate: default_wake_sample_rate(),
+            min_delay_secs: default_wake_min_delay_secs(),
+            max_delay_secs: default_wake_max_delay_secs(),
+        }
+    }
+}
+
+impl WakeRuleConfig {
+    /// Check the wake rule.
+    fn check(&self) -> ConfigReport {
+        let mut report = ConfigReport::new();
+        if !(0.0..=1.0).contains(&self.sample_rate) {
+            report.error("sample_rate", "must be between 0.0 and 1.0");
+        }
+        if self.max_delay_secs > 86_400 {
+            report.error("max_delay_secs", "must be at most 86400");
+        }
+        if self.min_delay_secs > self.max_delay_secs {
+            report.error("min_delay_secs", "must not exceed max_delay_secs");
+        }
+        report
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// DEGRADATION CONFIG
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Degradation tiers the fleet moves through while its RPC endpoints are
+/// partly available (see [`crate::degradation`]).
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DegradationConfig {
+    /// Probe the endpoints and degrade the fleet while they fail.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Seconds between two probes of the endpoints.
+    #[serde(default = "default_probe_interval_secs")]
+    pub probe_interval_secs: u64,
+
+    /// Probes in a row finding the endpoints worse off before the fleet
+    /// degrades.
+    #[serde(default = "default_degrade_after_probes")]
+    pub degrade_after_probes: u32,
+
+    /// Probes in a row finding the endpoints better off before the fleet
+    /// recovers.
+    #[serde(default = "default_recover_after_probes")]
+    pub recover_after_probes: u32,
+
+    /// URL each tier change is POSTed to as JSON.
+    #[serde(default)]
+    pub alert_webhook_url: Option<String>,
+
+    /// Seconds an alert POST may take.
+    #[serde(default = "default_webhook_timeout_secs")]
+    pub alert_webhook_timeout_secs: u64,
+}
+
+const fn default_probe_interval_secs() -> u64 {
+    30
+}
+
+fn default_degrade_after_probes() -> u32 {
+    DegradationSettings::default().degrade_after
+}
+
+fn default_recover_after_probes() -> u32 {
+    DegradationSettings::default().recover_after
+}
+
+impl Default for DegradationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            probe_interval_secs: default_probe_interval_secs(),
+            degrade_after_probes: default_degrade_after_probes(),
+            recover_after_probes: default_recover_after_probes(),
+            alert_webhook_url: None,
+            alert_webhook_timeout_secs: default_webhook_timeout_secs(),
+        }
+    }
+}
+
+impl DegradationConfig {
+    /// Convert to the settings tiers change with.
+    #[must_use]
+    pub const fn to_settings(&self) -> DegradationSettings {
+        DegradationSettings {
+            degrade_after: self.degrade_after_probes,
+            recover_after: self.recover_after_probes,
+        }
+    }
+
+    /// Check the degradation settings.
+    fn check(&self) -> ConfigReport {
+        let mut report = ConfigReport::new();
+        if !(1..=3600).contains(&self.probe_interval_secs) {
+            report.error("probe_interval_secs", "must be between 1 and 3600");
+        }
+        if self.degrade_after_probes == 0 {
+            report.error("degrade_after_probes", "must be > 0");
+        }
+        if self.recover_after_probes == 0 {
+            report.error("recover_after_probes", "must be > 0");
+        }
+        if let Some(url) = &self.alert_webhook_url
+            && !(url.starts_with("http://") || url.starts_with("https://"))
+        {
+            report.error("alert_webhook_url", "must be an http:// or https:// URL");
+        }
+        if self.alert_webhook_timeout_secs == 0 {
+            report.error("alert_webhook_timeout_secs", "must be > 0");
+        }
+        report
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// CHAIN CONFIG
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Chain/network configuration.
+///
+/// Used for `[chain]` as well as for each `[chains.<name>]` profile.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ChainConfig {
+    /// Chain ID.
+    pub chain_id: u64,
+
+    /// HTTP RPC URL.
+    pub rpc_url: String,
+
+    /// WebSocket RPC URL, for subscriptions.
+    pub ws_url: Option<String>,
+
+    /// Chain type for provider selection.
+    #[serde(default = "default_chain_type")]
+    pub chain_type: String,
+
+    /// Gas limit override (for chains with unreliable estimation).
+    pub gas_limit_override: Option<u64>,
+
+    /// Use realtime API if available (MegaETH).
+    #[serde(default)]
+    pub use_realtime: bool,
+
+    /// Submit the engine's transactions with a plain send and receipt
+    /// polling even where the endpoint supports the realtime API, for
+    /// debugging.
+    #[serde(default)]
+    pub force_standard_submission: bool,
+
+    /// GHOSTNET contract addresses on this chain.
+    #[serde(default)]
+    pub ghostnet: ContractAddresses,
+
+    /// JSON deployment manifest whose addresses override `ghostnet`.
+    pub addresses_file: Option<PathBuf>,
+
+    /// More RPC endpoints to spread wallets over, besides `rpc_url`.
+    #[serde(default)]
+    pub endpoints: Vec<EndpointConfig>,
+
+    /// How wallets are assigned to endpoints.
+    #[serde(default)]
+    pub endpoint_assignment: AssignmentStrategy,
+
+    /// Consecutive failures after which an endpoint's wallets are moved to
+    /// another one.
+    #[serde(default = "default_endpoint_failure_threshold")]
+    pub endpoint_failure_threshold: u32,
+
+    /// Seconds a failing endpoint waits before it gets its wallets back.
+    #[serde(default = "default_endpoint_retry_secs")]
+    pub endpoint_retry_secs: u64,
+}
+
+impl ChainConfig {
+    /// Merge the addresses of `addresses_file`, if set, over `ghostnet`.
+    ///
+    /// `key` names this chain in error messages.
+    fn load_addresses_file(&mut self, key: &str) -> Result<()> {
+        let Some(path) = &self.addresses_file else {
+            return Ok(());
+        };
+        debug!(path = %path.display(), "Loading deployment manifest");
+
+        let content = fs::read_to_string(path).map_err(|e| ConfigError::FileRead {
+            path: path.clone(),
+            source: e,
+        })?;
+        let manifest: DeploymentManifest =
+            serde_json::from_str(&content).map_err(|e| ConfigError::Manifest {
+                path: path.clone(),
+                source: e,
+            })?;
+
+        if let Some(chain_id) = manifest.chain_id
+            && chain_id != self.chain_id
+        {
+            return Err(ConfigError::Validation(format!(
+                "{} is for chain {chain_id} but {key}.chain_id is {}",
+                path.display(),
+                self.chain_id
+            ))
+            .into());
+        }
+
+        self.ghostnet = self.ghostnet.merge(&manifest.addresses);
+        Ok(())
+    }
+
+    /// Name of the endpoint of `rpc_url` among [`endpoints`](Self::endpoints).
+    pub const PRIMARY_ENDPOINT: &str = "primary";
+
+    /// Check the extra endpoints.
+    fn check_endpoints(&self) -> ConfigReport {
+        let mut report = ConfigReport::new();
+        if self.endpoint_failure_threshold == 0 {
+            report.error("endpoint_failure_threshold", "must be > 0");
+        }
+        for (i, endpoint) in self.endpoints.iter().enumerate() {
+            let invalid = if endpoint.name.is_empty() || endpoint.url.is_empty() {
+                Some("needs a name and a url")
+            } else if endpoint.weight == 0 {
+                Some("weight must be > 0")
+            } else if endpoint.name == Self::PRIMARY_ENDPOINT
+                || self.endpoints[..i].iter().any(|other| other.name == endpoint.name)
+            {
+                Some("name is already used")
+            } else {
+                None
+            };
+            if let Some(reason) = invalid {
+                report.error(format!("endpoints[{i}]"), reason);
+            }
+        }
+        report
+    }
+}
+
+impl Default for ChainConfig {
+    /// Placeholder for a config without `[chain]`, replaced by
+    /// [`Settings::select_chain`].
+    fn default() -> Self {
+        Self {
+            chain_id: 0,
+            rpc_url: String::new(),
+            ws_url: None,
+            chain_type: default_chain_type(),
+            gas_limit_override: None,
+            use_realtime: false,
+            force_standard_submission: false,
+            ghostnet: ContractAddresses::default(),
+            addresses_file: None,
+            endpoints: Vec::new(),
+            endpoint_assignment: AssignmentStrategy::default(),
+            endpoint_failure_threshold: default_endpoint_failure_threshold(),
+            endpoint_retry_secs: default_endpoint_retry_secs(),
+        }
+    }
+}
+
+fn default_chain_type() -> String {
+    "standard".into()
+}
+
+const fn default_endpoint_failure_threshold() -> u32 {
+    3
+}
+
+const fn default_endpoint_retry_secs() -> u64 {
+    60
+}
+
+/// An RPC endpoint of `[chain].endpoints`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct EndpointConfig {
+    /// Name for logs and metrics.
+    pub name: String,
+
+    /// HTTP RPC URL.
+    pub url: String,
+
+    /// Share of the wallets under `weighted` assignment; `rpc_url` has
+    /// weight 1.
+    #[serde(default = "default_endpoint_weight")]
+    pub weight: u32,
+}
+
+const fn default_endpoint_weight() -> u32 {
+    1
+}
+
+/// GHOSTNET contract addresses of one deployment.
+///
+/// Deserializes from the `ghostnet` table of a chain (snake case) as well as
+/// from a deployment manifest (camel case, as in the web app's address list).
+/// Empty strings in a manifest count as not deployed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ContractAddresses {
+    /// GhostCore contract address.
+    #[serde(default, alias = "ghostCore", deserialize_with = "deserialize_address")]
+    pub ghost_core: Option<Address>,
 
     /// HashCrash contract address.
-    pub hash_crash: Address,
+    #[serde(default, alias = "hashCrash", deserialize_with = "deserialize_address")]
+    pub hash_crash: Option<Address>,
+
+    /// ArcadeCore contract address.
+    #[serde(default, alias = "arcadeCore", deserialize_with = "deserialize_address")]
+    pub arcade_core: Option<Address>,
+
+    /// DATA token address.
+    #[serde(default, alias = "dataToken", deserialize_with = "deserialize_address")]
+    pub data_token: Option<Address>,
+
+    /// DeadPool contract address, optional: without it no rounds are bet on.
+    #[serde(default, alias = "deadPool", deserialize_with = "deserialize_address")]
+    pub dead_pool: Option<Address>,
+}
+
+impl ContractAddresses {
+    /// These addresses with those set in `overrides` replaced.
+    #[must_use]
+    pub fn merge(&self, overrides: &Self) -> Self {
+        Self {
+            ghost_core: overrides.ghost_core.or(self.ghost_core),
+            hash_crash: overrides.hash_crash.or(self.hash_crash),
+            arcade_core: overrides.arcade_core.or(self.arcade_core),
+            data_token: overrides.data_token.or(self.data_token),
+            dead_pool: overrides.dead_pool.or(self.dead_pool),
+        }
+    }
+
+    /// Config keys of the required addresses that are not set.
+    #[must_use]
+    pub fn missing(&self) -> Vec<&'static str> {
+        [
+            ("ghost_core", self.ghost_core),
+            ("hash_crash", self.hash_crash),
+            ("arcade_core", self.arcade_core),
+            ("data_token", self.data_token),
+        ]
+        .into_iter()
+        .filter_map(|(key, address)| address.is_none().then_some(key))
+        .collect()
+    }
+}
+
+/// JSON deployment manifest of a chain.
+///
+/// ```json
+/// {
+///   "chainId": 6343,
+///   "ghostCore": "0x...",
+///   "hashCrash": "0x...",
+///   "arcadeCore": "0x...",
+///   "dataToken": "0x..."
+/// }
+/// ```
+///
+/// Other keys, such as the addresses of contracts the fleet does not use, are
+/// ignored.
+#[derive(Debug, Deserialize)]
+struct DeploymentManifest {
+    /// Chain the contracts are deployed on.
+    #[serde(default, alias = "chainId")]
+    chain_id: Option<u64>,
+
+    /// Contract addresses.
+    #[serde(flatten)]
+    addresses: ContractAddresses,
+}
+
+/// Deserialize an optional address, treating an empty string as unset.
+fn deserialize_address<'de, D>(deserializer: D) -> std::result::Result<Option<Address>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    match Option::<String>::deserialize(deserializer)?.as_deref() {
+        None | Some("") => Ok(None),
+        Some(address) => address.parse().map(Some).map_err(serde::de::Error::custom),
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// FLEET CONFIG
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// One of several fleets run side by side, `[fleet.<name>]`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct FleetConfig {
+    /// Wallets of this fleet. Ids only need to be unique within the fleet.
+    #[serde(default)]
+    pub wallets: Vec<WalletConfig>,
+
+    /// Behavior profiles of this fleet, added to the shared `[profiles]`.
+    #[serde(default)]
+    pub profiles: HashMap<String, ProfileConfig>,
+
+    /// Plugin configuration of this fleet, in place of the shared
+    /// `[plugins]`.
+    #[serde(default)]
+    pub plugins: Option<PluginsConfig>,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// WALLET CONFIG
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Individual wallet configuration.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct WalletConfig {
+    /// Unique wallet identifier.
+    pub id: String,
+
+    /// Wallet address.
+    pub address: Address,
+
+    /// Behavior profile name.
+    pub profile: String,
+
+    /// Where the wallet's signing key comes from.
+    #[serde(default)]
+    pub key_source: Option<KeySource>,
+
+    /// Private key (hex, with or without 0x prefix).
+    ///
+    /// Shorthand for a raw [`KeySource`], for local development only.
+    #[serde(default)]
+    pub private_key: Option<Sensitive<String>>,
+
+    /// Whether this wallet is enabled.
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    /// Group of related wallets, limited by `[groups.<name>]`.
+    #[serde(default)]
+    pub group: Option<String>,
+
+    /// Spend caps, overriding those of the wallet's profile cap by cap.
+    #[serde(default)]
+    pub budget: BudgetConfig,
+
+    /// Wind the wallet down and retire it, see `[retirement]`.
+    #[serde(default)]
+    pub retiring: bool,
+
+    /// Address a retiring wallet's remaining balances are swept to.
+    #[serde(default)]
+    pub sweep_to: Option<Address>,
+
+    /// Hold the wallet back until it replaces a retired one.
+    #[serde(default)]
+    pub standby: bool,
+}
+
+const fn default_true() -> bool {
+    true
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// KEY SOURCES
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Source of a wallet's signing key.
+///
+/// ```toml
+/// key_source = { type = "keystore", path = "keys/whale_1.json", password_env = "WHALE_1_PW" }
+/// key_source = { type = "mnemonic", mnemonic = "fleet", index = 3 }
+/// key_source = { type = "raw", private_key = "0x..." }
+/// key_source = { type = "remote", url = "http://web3signer:9000", timeout_secs = 10 }
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum KeySource {
+    /// Hex private key in the config file. For local development only.
+    Raw {
+        /// Private key (hex, with or without 0x prefix).
+        private_key: Sensitive<String>,
+    },
+
+    /// Encrypted JSON keystore file (Web3 Secret Storage format, as written
+    /// by geth or `cast wallet import`).
+    Keystore {
+        /// Path to the keystore file.
+        path: PathBuf,
+
+        /// Environment variable holding the password; prompted for at
+        /// startup if not set.
+        #[serde(default)]
+        password_env: Option<String>,
+    },
+
+    /// Key derived from a seed phrase of `[mnemonics.<name>]`.
+    Mnemonic {
+        /// Name of the mnemonic.
+        mnemonic: String,
+
+        /// Index appended to the mnemonic's derivation path.
+        index: u32,
+    },
+
+    /// Key held by a remote signer serving `eth_signTransaction`, such as
+    /// web3signer in `eth1` mode. The key never enters the process; the
+    /// signer must hold the key of the wallet's `address`.
+    Remote {
+        /// JSON-RPC endpoint of the signer.
+        url: String,
+
+        /// Time the signer has to answer, in seconds.
+        #[serde(default = "default_remote_signer_timeout_secs")]
+        timeout_secs: u64,
+    },
+}
+
+const fn default_remote_signer_timeout_secs() -> u64 {
+    10
+}
+
+/// Seed phrase that wallet keys are derived from.
+///
+/// Wallet `index` of the mnemonic uses the key at `<derivation_path>/<index>`,
+/// so the same phrase always yields the same addresses.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MnemonicConfig {
+    /// Environment variable holding the phrase; prompted for at startup if
+    /// not set.
+    #[serde(default)]
+    pub phrase_env: Option<String>,
+
+    /// BIP-32 derivation path without the final index.
+    #[serde(default = "default_derivation_path")]
+    pub derivation_path: String,
+}
+
+fn default_derivation_path() -> String {
+    "m/44'/60'/0'/0".into()
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// GROUP CONFIG
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Action limit of a wallet group.
+///
+/// At most `max_actions` actions across all wallets of the group within any
+/// `window_secs` seconds. Wallets that would exceed it are rescheduled.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+pub struct GroupConfig {
+    /// Maximum actions within the window.
+    pub max_actions: u32,
+
+    /// Length of the sliding window in seconds.
+    pub window_secs: u64,
+}
+
+impl GroupConfig {
+    /// Convert to a scheduler group limit.
+    #[must_use]
+    pub fn to_limit(self) -> fleet_core::scheduler::GroupLimit {
+        let window_secs = i64::try_from(self.window_secs).unwrap_or(i64::MAX);
+        fleet_core::scheduler::GroupLimit::new(
+            self.max_actions,
+            chrono::Duration::seconds(window_secs),
+        )
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// PLUGINS CONFIG
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Plugin configuration.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct PluginsConfig {
+    /// List of enabled plugin IDs.
+    ///
+    /// Plugins of equal priority are considered in this order.
+    #[serde(default)]
+    pub enabled: Vec<String>,
+
+    /// How to choose when several plugins want to act for the same wallet.
+    #[serde(default)]
+    pub selection: SelectionStrategy,
+
+    /// Plugin priorities by plugin ID (default: [`DEFAULT_PRIORITY`]).
+    #[serde(default)]
+    pub priorities: HashMap<String, Priority>,
+
+    /// When plugins that keep failing are disabled.
+    #[serde(default)]
+    pub health: PluginHealthConfig,
+
+    /// GHOSTNET plugin configuration.
+    pub ghostnet: Option<GhostnetPluginConfig>,
+}
+
+impl PluginsConfig {
+    /// Priority of a plugin.
+    #[must_use]
+    pub fn priority(&self, plugin_id: &str) -> Priority {
+        self.priorities
+            .get(plugin_id)
+            .copied()
+            .unwrap_or(DEFAULT_PRIORITY)
+    }
+}
+
+/// When plugins that keep failing are disabled, and probed for recovery (see
+/// [`PluginHealth`](fleet_core::plugins::PluginHealth)).
+///
+/// Error rates are counted for decisions and executions separately.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PluginHealthConfig {
+    /// Disable plugins that fail too often. Without it, plugins are only
+    /// disabled by hand (`ctl plugin disable <id>`).
+    #[serde(default = "default_true")]
+    pub auto_disable: bool,
 
-    /// ArcadeCore contract address.
-    pub arcade_core: Address,
+    /// Window over which calls are counted, in seconds.
+    #[serde(default = "default_health_window_secs")]
+    pub window_secs: u64,
 
-    /// DATA token address.
-    pub data_token: Address,
+    /// Fewest calls of a kind within the window before their error rate
+    /// counts.
+    #[serde(default = "default_health_min_calls")]
+    pub min_calls: u32,
+
+    /// Share of failed calls of a kind above which the plugin is disabled
+    /// (0.0 - 1.0).
+    #[serde(default = "default_health_max_error_rate")]
+    pub max_error_rate: f64,
+
+    /// Time between probes of a disabled plugin, in seconds.
+    #[serde(default = "default_health_probe_interval_secs")]
+    pub probe_interval_secs: u64,
+}
+
+fn default_health_window_secs() -> u64 {
+    HealthSettings::default().window.as_secs()
+}
+
+fn default_health_min_calls() -> u32 {
+    HealthSettings::default().min_calls
+}
+
+fn default_health_max_error_rate() -> f64 {
+    HealthSettings::default().max_error_rate
+}
+
+fn default_health_probe_interval_secs() -> u64 {
+    HealthSettings::default().probe_interval.as_secs()
+}
+
+impl Default for PluginHealthConfig {
+    fn default() -> Self {
+        Self {
+            auto_disable: true,
+            window_secs: default_health_window_secs(),
+            min_calls: default_health_min_calls(),
+            max_error_rate: default_health_max_error_rate(),
+            probe_interval_secs: default_health_probe_interval_secs(),
+        }
+    }
+}
+
+impl PluginHealthConfig {
+    /// Convert to the settings plugins are judged by.
+    #[must_use]
+    pub const fn to_settings(&self) -> HealthSettings {
+        HealthSettings {
+            auto_disable: self.auto_disable,
+            window: std::time::Duration::from_secs(self.window_secs),
+            min_calls: self.min_calls,
+            max_error_rate: self.max_error_rate,
+            probe_interval: std::time::Duration::from_secs(self.probe_interval_secs),
+        }
+    }
 
+    /// Check the plugin health settings.
+    fn check(&self) -> ConfigReport {
+        let mut report = ConfigReport::new();
+        if self.window_secs == 0 {
+            report.error("window_secs", "must be > 0");
+        }
+        if self.min_calls == 0 {
+            report.error("min_calls", "must be > 0");
+        }
+        if !(0.0..=1.0).contains(&self.max_error_rate) {
+            report.error("max_error_rate", "must be between 0.0 and 1.0");
+        }
+        if self.probe_interval_secs == 0 {
+            report.error("probe_interval_secs", "must be > 0");
+        }
+        report
+    }
+}
+
+/// GHOSTNET-specific plugin configuration.
+///
+/// The contract addresses are per chain, see [`ChainConfig::ghostnet`].
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[allow(clippy::struct_excessive_bools)] // Independent toggles, not states
+pub struct GhostnetPluginConfig {
     /// Minimum stake amount (in wei).
     #[serde(default = "default_min_stake")]
     pub min_stake: String,
@@ -311,12 +2482,219 @@ pub struct GhostnetPluginConfig {
     /// Enable HashCrash arcade game.
     #[serde(default)]
     pub hashcrash_enabled: bool,
+
+    /// Maximum age of a DATA balance before it is re-read (seconds).
+    ///
+    /// The plugin refuses to size stakes from an older balance.
+    #[serde(default = "default_max_balance_age")]
+    pub max_balance_age_secs: u64,
+
+    /// HashCrash losses in a row after which bets are cut down (0 disables).
+    #[serde(default = "default_hashcrash_loss_streak")]
+    pub hashcrash_loss_streak: u32,
+
+    /// Fraction of the usual bet size to bet after a losing streak (0.0 - 1.0).
+    #[serde(default = "default_hashcrash_loss_streak_bet_pct")]
+    pub hashcrash_loss_streak_bet_pct: f64,
+
+    /// Apply death reduction boosts.
+    #[serde(default = "default_boosts_enabled")]
+    pub death_reduction_boosts: bool,
+
+    /// Apply yield multiplier boosts.
+    #[serde(default = "default_boosts_enabled")]
+    pub yield_multiplier_boosts: bool,
+
+    /// Most DATA a wallet spends on boosts in total (in wei).
+    #[serde(default = "default_max_boost_spend")]
+    pub max_boost_spend: String,
+
+    /// Share of pending rewards paid at most for one boost (0.0 - 1.0),
+    /// scaled by the profile's risk tolerance.
+    #[serde(default = "default_max_boost_reward_share")]
+    pub max_boost_reward_share: f64,
+
+    /// DATA kept in the wallet when sizing stakes and bets (in wei).
+    #[serde(default = "default_data_reserve")]
+    pub data_reserve: String,
+
+    /// Death rates positions are valued with, per level (basis points).
+    /// Unset levels use the protocol's base rate.
+    #[serde(default)]
+    pub death_rates: DeathRateTable,
+
+    /// Edge of extracting over holding one more scan a fully patient profile
+    /// wants before extracting (basis points), scaled by patience.
+    #[serde(default = "default_min_extract_edge_bps")]
+    pub min_extract_edge_bps: u64,
+
+    /// Least DATA an extraction must bring in after its toll and gas (in
+    /// wei), unless the position is about to be culled.
+    #[serde(default = "default_min_net_extract")]
+    pub min_net_extract: String,
+
+    /// Estimated gas cost of an extraction, priced in DATA (in wei).
+    #[serde(default = "default_extract_gas_cost")]
+    pub extract_gas_cost: String,
+
+    /// Culling risk from which positions are extracted regardless (basis
+    /// points).
+    #[serde(default = "default_imminent_cull_risk_bps")]
+    pub imminent_cull_risk_bps: u16,
+
+    /// Exit toll assumed while GhostCore's cannot be read (basis points).
+    #[serde(default)]
+    pub default_exit_toll_bps: u16,
+
+    /// How long an exit toll read from GhostCore is reused (seconds).
+    #[serde(default = "default_exit_toll_ttl")]
+    pub exit_toll_ttl_secs: u64,
+
+    /// How close GhostCore's system reset may be before wallets stop staking
+    /// and extract their positions (seconds, 0 disables).
+    #[serde(default = "default_reset_danger_secs")]
+    pub reset_danger_secs: u64,
+
+    /// How long a reset timer read from GhostCore is reused (seconds).
+    #[serde(default = "default_reset_timer_ttl")]
+    pub reset_timer_ttl_secs: u64,
+
+    /// How long DataToken's tax rate and exclusions are reused (seconds).
+    #[serde(default = "default_transfer_tax_ttl")]
+    pub transfer_tax_ttl_secs: u64,
+
+    /// Whether positions are extracted in full or in parts.
+    #[serde(default)]
+    pub extract_strategy: ExtractStrategy,
+
+    /// Approve GhostCore before each stake its DATA allowance does not
+    /// cover, staking shortly after as a follow-up.
+    #[serde(default)]
+    pub approve_before_stake: bool,
+
+    /// Probability of re-entering at another level shortly after a full
+    /// extraction (0.0 - 1.0).
+    #[serde(default)]
+    pub reentry_probability: f64,
+
+    /// Hold positions a few scans short of a ghost streak milestone instead
+    /// of extracting them.
+    #[serde(default)]
+    pub streak_protection: StreakProtection,
+
+    /// Play the games registered with ArcadeCore besides HashCrash.
+    #[serde(default = "default_arcade_enabled")]
+    pub arcade_enabled: bool,
+
+    /// ArcadeCore game IDs that may (all if empty) or may never be played.
+    #[serde(default)]
+    pub arcade_games: GameFilter,
+
+    /// Bet on DeadPool rounds, if the chain has a DeadPool.
+    #[serde(default = "default_dead_pool_enabled")]
+    pub dead_pool_enabled: bool,
+
+    /// How DeadPool rounds are picked and bet on.
+    #[serde(default)]
+    pub dead_pool: DeadPoolSettings,
+
+    /// Margin on gas estimates and most gas an action's transaction may use.
+    #[serde(default)]
+    pub gas: GasSettings,
+}
+
+impl GhostnetPluginConfig {
+    /// Check that the amounts are integer amounts in wei.
+    ///
+    /// Ranges are checked by [`GhostnetConfig::validate`].
+    fn check(&self) -> ConfigReport {
+        let mut report = ConfigReport::new();
+        for (key, amount) in [
+            ("min_net_extract", &self.min_net_extract),
+            ("extract_gas_cost", &self.extract_gas_cost),
+            ("max_boost_spend", &self.max_boost_spend),
+            ("data_reserve", &self.data_reserve),
+        ] {
+            if amount.parse::<u128>().is_err() {
+                report.error(key, format!("is not a wei amount: {amount}"));
+            }
+        }
+        report
+    }
 }
 
 fn default_min_stake() -> String {
     "1000000000000000000".into() // 1 DATA
 }
 
+const fn default_max_balance_age() -> u64 {
+    ghostnet_actions::config::BehaviorSettings::default_max_balance_age_secs()
+}
+
+const fn default_hashcrash_loss_streak() -> u32 {
+    ghostnet_actions::config::BehaviorSettings::default_hashcrash_loss_streak()
+}
+
+const fn default_hashcrash_loss_streak_bet_pct() -> f64 {
+    ghostnet_actions::config::BehaviorSettings::default_hashcrash_loss_streak_bet_pct()
+}
+
+const fn default_boosts_enabled() -> bool {
+    ghostnet_actions::config::BehaviorSettings::default_boosts_enabled()
+}
+
+fn default_max_boost_spend() -> String {
+    ghostnet_actions::config::BehaviorSettings::default_max_boost_spend().to_string()
+}
+
+fn default_data_reserve() -> String {
+    "0".into()
+}
+
+const fn default_max_boost_reward_share() -> f64 {
+    ghostnet_actions::config::BehaviorSettings::default_max_boost_reward_share()
+}
+
+const fn default_min_extract_edge_bps() -> u64 {
+    ghostnet_actions::config::BehaviorSettings::default_min_extract_edge_bps()
+}
+
+fn default_min_net_extract() -> String {
+    ghostnet_actions::config::BehaviorSettings::default_min_net_extract().to_string()
+}
+
+fn default_extract_gas_cost() -> String {
+    "0".into()
+}
+
+const fn default_imminent_cull_risk_bps() -> u16 {
+    ghostnet_actions::config::BehaviorSettings::default_imminent_cull_risk_bps()
+}
+
+const fn default_exit_toll_ttl() -> u64 {
+    ghostnet_actions::config::BehaviorSettings::default_exit_toll_ttl_secs()
+}
+
+const fn default_reset_danger_secs() -> u64 {
+    ghostnet_actions::config::BehaviorSettings::default_reset_danger_secs()
+}
+
+const fn default_reset_timer_ttl() -> u64 {
+    ghostnet_actions::config::BehaviorSettings::default_reset_timer_ttl_secs()
+}
+
+const fn default_transfer_tax_ttl() -> u64 {
+    ghostnet_actions::config::BehaviorSettings::default_transfer_tax_ttl_secs()
+}
+
+const fn default_arcade_enabled() -> bool {
+    ghostnet_actions::config::BehaviorSettings::default_plays_arcade()
+}
+
+const fn default_dead_pool_enabled() -> bool {
+    ghostnet_actions::config::BehaviorSettings::default_plays_dead_pool()
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // SAFETY CONFIG
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -339,12 +2717,117 @@ pub struct SafetyConfig {
     /// Global pause switch.
     #[serde(default)]
     pub global_pause: bool,
+
+    /// In-place retries of an action that failed with a transient error.
+    ///
+    /// Only once these are used up does the error count toward the
+    /// wallet's circuit breaker.
+    #[serde(default = "default_transient_retries")]
+    pub transient_retries: u32,
+
+    /// Pause before each in-place retry, in milliseconds.
+    #[serde(default = "default_transient_retry_delay")]
+    pub transient_retry_delay_ms: u64,
+
+    /// How long a rate-limited wallet, or the whole fleet once the global
+    /// breaker trips, holds off, in seconds.
+    #[serde(default = "default_rate_limit_backoff")]
+    pub rate_limit_backoff_secs: u64,
+
+    /// Rate-limited errors within `rate_limit_window_secs` that trip the
+    /// global breaker.
+    #[serde(default = "default_max_rate_limited")]
+    pub max_rate_limited_errors: u32,
+
+    /// Window over which rate-limited errors are counted, in seconds.
+    #[serde(default = "default_rate_limit_window")]
+    pub rate_limit_window_secs: u64,
+
+    /// How long to wait for an action's transaction to be mined before
+    /// replacing it, in seconds.
+    #[serde(default = "default_receipt_timeout")]
+    pub receipt_timeout_secs: u64,
+
+    /// Receipt timeouts of specific actions, by action ID, in seconds.
+    #[serde(default)]
+    pub action_receipt_timeout_secs: HashMap<String, u64>,
+
+    /// Replacements sent for a transaction that is not mined in time,
+    /// before it is abandoned (at most 3).
+    #[serde(default = "default_max_replacements")]
+    pub max_replacements: u32,
+
+    /// Fee increase of each replacement over the previous transaction, in
+    /// percent. Nodes reject replacements that bump by less than 10%.
+    #[serde(default = "default_replacement_fee_bump")]
+    pub replacement_fee_bump_pct: u32,
+
+    /// How long a plugin may take to decide an action before it is
+    /// cancelled, in seconds.
+    #[serde(default = "default_decide_timeout")]
+    pub decide_timeout_secs: u64,
+
+    /// How long a plugin may take to execute an action before it is
+    /// cancelled, in seconds.
+    #[serde(default = "default_execute_timeout")]
+    pub execute_timeout_secs: u64,
+
+    /// Timeouts of specific plugins, by plugin ID.
+    #[serde(default)]
+    pub plugin_timeouts: HashMap<String, PluginTimeoutConfig>,
+
+    /// Spend caps of the fleet as a whole.
+    #[serde(default)]
+    pub budget: BudgetConfig,
 }
 
+/// Most replacements sent for one transaction.
+const MAX_REPLACEMENTS: u32 = 3;
+
 const fn default_max_errors() -> u32 {
     5
 }
 
+const fn default_transient_retries() -> u32 {
+    2
+}
+
+const fn default_transient_retry_delay() -> u64 {
+    500
+}
+
+const fn default_rate_limit_backoff() -> u64 {
+    300 // 5 minutes
+}
+
+const fn default_max_rate_limited() -> u32 {
+    10
+}
+
+const fn default_rate_limit_window() -> u64 {
+    60
+}
+
+const fn default_receipt_timeout() -> u64 {
+    60
+}
+
+const fn default_max_replacements() -> u32 {
+    3
+}
+
+const fn default_replacement_fee_bump() -> u32 {
+    12
+}
+
+const fn default_decide_timeout() -> u64 {
+    10
+}
+
+const fn default_execute_timeout() -> u64 {
+    120
+}
+
 const fn default_cooldown() -> u64 {
     3600 // 1 hour
 }
@@ -353,13 +2836,355 @@ const fn default_max_actions() -> u32 {
     20
 }
 
-impl Default for SafetyConfig {
+impl SafetyConfig {
+    /// Check that the breakers can trip.
+    fn check(&self) -> ConfigReport {
+        let mut report = ConfigReport::new();
+        if self.max_consecutive_errors == 0 {
+            report.error("max_consecutive_errors", "must be > 0");
+        }
+        if self.max_rate_limited_errors == 0 {
+            report.error("max_rate_limited_errors", "must be > 0");
+        }
+        if self.max_replacements > MAX_REPLACEMENTS {
+            report.error("max_replacements", format!("must be at most {MAX_REPLACEMENTS}"));
+        }
+        if self.replacement_fee_bump_pct < 10 {
+            report.error("replacement_fee_bump_pct", "must be at least 10");
+        }
+        if self.decide_timeout_secs == 0 {
+            report.error("decide_timeout_secs", "must be > 0");
+        }
+        if self.execute_timeout_secs == 0 {
+            report.error("execute_timeout_secs", "must be > 0");
+        }
+        for (plugin, timeouts) in &self.plugin_timeouts {
+            if timeouts.decide_secs == Some(0) {
+                report.error(format!("plugin_timeouts.{plugin}.decide_secs"), "must be > 0");
+            }
+            if timeouts.execute_secs == Some(0) {
+                report.error(format!("plugin_timeouts.{plugin}.execute_secs"), "must be > 0");
+            }
+        }
+        report
+    }
+}
+
+impl Default for SafetyConfig {
+    fn default() -> Self {
+        Self {
+            max_consecutive_errors: default_max_errors(),
+            cooldown_secs: default_cooldown(),
+            max_actions_per_hour: default_max_actions(),
+            global_pause: false,
+            transient_retries: default_transient_retries(),
+            transient_retry_delay_ms: default_transient_retry_delay(),
+            rate_limit_backoff_secs: default_rate_limit_backoff(),
+            max_rate_limited_errors: default_max_rate_limited(),
+            rate_limit_window_secs: default_rate_limit_window(),
+            receipt_timeout_secs: default_receipt_timeout(),
+            action_receipt_timeout_secs: HashMap::new(),
+            max_replacements: default_max_replacements(),
+            replacement_fee_bump_pct: default_replacement_fee_bump(),
+            decide_timeout_secs: default_decide_timeout(),
+            execute_timeout_secs: default_execute_timeout(),
+            plugin_timeouts: HashMap::new(),
+            budget: BudgetConfig::default(),
+        }
+    }
+}
+
+/// Timeouts of one plugin, overriding those of `[safety]`.
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize)]
+pub struct PluginTimeoutConfig {
+    /// How long the plugin may take to decide an action, in seconds.
+    #[serde(default)]
+    pub decide_secs: Option<u64>,
+
+    /// How long the plugin may take to execute an action, in seconds.
+    #[serde(default)]
+    pub execute_secs: Option<u64>,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// BUDGET CONFIG
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Spend caps of the fleet, of a profile's wallets or of a single wallet.
+///
+/// Gas and DATA amounts are integer amounts in wei. Unset caps are unlimited.
+/// Daily caps apply to a rolling day from the first spend after the previous
+/// day ran out.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct BudgetConfig {
+    /// Native gas per day, in wei.
+    #[serde(default)]
+    pub daily_gas_wei: Option<String>,
+
+    /// DATA staked or bet per day, in wei.
+    #[serde(default)]
+    pub daily_data_wei: Option<String>,
+
+    /// Actions per day.
+    #[serde(default)]
+    pub daily_actions: Option<u64>,
+
+    /// Native gas overall, in wei.
+    #[serde(default)]
+    pub lifetime_gas_wei: Option<String>,
+
+    /// DATA staked or bet overall, in wei.
+    #[serde(default)]
+    pub lifetime_data_wei: Option<String>,
+
+    /// Actions overall.
+    #[serde(default)]
+    pub lifetime_actions: Option<u64>,
+}
+
+impl BudgetConfig {
+    /// These caps, with unset ones taken from `defaults`.
+    #[must_use]
+    pub fn or(&self, defaults: &Self) -> Self {
+        Self {
+            daily_gas_wei: self.daily_gas_wei.clone().or_else(|| defaults.daily_gas_wei.clone()),
+            daily_data_wei: self.daily_data_wei.clone().or_else(|| defaults.daily_data_wei.clone()),
+            daily_actions: self.daily_actions.or(defaults.daily_actions),
+            lifetime_gas_wei: self
+                .lifetime_gas_wei
+                .clone()
+                .or_else(|| defaults.lifetime_gas_wei.clone()),
+            lifetime_data_wei: self
+                .lifetime_data_wei
+                .clone()
+                .or_else(|| defaults.lifetime_data_wei.clone()),
+            lifetime_actions: self.lifetime_actions.or(defaults.lifetime_actions),
+        }
+    }
+
+    /// Convert to fleet-core budget caps.
+    ///
+    /// Amounts are expected to have been validated; any that do not parse
+    /// are left unlimited.
+    #[must_use]
+    pub fn to_caps(&self) -> BudgetCaps {
+        let amount = |cap: &Option<String>| cap.as_deref().and_then(|a| a.parse::<U256>().ok());
+        BudgetCaps {
+            daily: SpendLimit {
+                gas_wei: amount(&self.daily_gas_wei),
+                data: amount(&self.daily_data_wei),
+                actions: self.daily_actions,
+            },
+            lifetime: SpendLimit {
+                gas_wei: amount(&self.lifetime_gas_wei),
+                data: amount(&self.lifetime_data_wei),
+                actions: self.lifetime_actions,
+            },
+        }
+    }
+
+    /// Check whether no cap is set.
+    #[must_use]
+    pub const fn is_unlimited(&self) -> bool {
+        self.daily_gas_wei.is_none()
+            && self.daily_data_wei.is_none()
+            && self.daily_actions.is_none()
+            && self.lifetime_gas_wei.is_none()
+            && self.lifetime_data_wei.is_none()
+            && self.lifetime_actions.is_none()
+    }
+
+    /// Check that the amounts are integer amounts in wei, and warn about
+    /// daily caps that the lifetime caps make pointless.
+    fn check(&self) -> ConfigReport {
+        let mut report = ConfigReport::new();
+        for (key, amount) in [
+            ("daily_gas_wei", &self.daily_gas_wei),
+            ("daily_data_wei", &self.daily_data_wei),
+            ("lifetime_gas_wei", &self.lifetime_gas_wei),
+            ("lifetime_data_wei", &self.lifetime_data_wei),
+        ] {
+            if let Some(amount) = amount {
+                check_wei(&mut report, key, amount);
+            }
+        }
+        let caps = self.to_caps();
+        let pairs = [
+            ("gas_wei", caps.daily.gas_wei, caps.lifetime.gas_wei),
+            ("data_wei", caps.daily.data, caps.lifetime.data),
+            (
+                "actions",
+                caps.daily.actions.map(U256::from),
+                caps.lifetime.actions.map(U256::from),
+            ),
+        ];
+        for (key, daily, lifetime) in pairs {
+            if let (Some(daily), Some(lifetime)) = (daily, lifetime)
+                && daily > lifetime
+            {
+                report.warning(
+                    format!("daily_{key}"),
+                    format!("is above lifetime_{key} and never reached"),
+                );
+            }
+        }
+        report
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// SIMULATION CONFIG
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Settings of a simulated run (see [`crate::simulation`]).
+///
+/// Simulated transactions revert, get dropped and use gas according to these
+/// distributions; every wallet starts with the given balances.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SimulationConfig {
+    /// Seed for all randomness of the run.
+    #[serde(default)]
+    pub seed: u64,
+
+    /// Virtual start time.
+    ///
+    /// Fixed by default so that runs with the same seed are comparable.
+    #[serde(default = "default_simulation_start")]
+    pub start: DateTime<Utc>,
+
+    /// Simulated time span in days.
+    #[serde(default = "default_simulation_days")]
+    pub days: u32,
+
+    /// Fraction of mined transactions that revert (0.0 - 1.0).
+    #[serde(default = "default_revert_rate")]
+    pub revert_rate: f64,
+
+    /// Fraction of transactions that are never mined (0.0 - 1.0).
+    #[serde(default = "default_drop_rate")]
+    pub drop_rate: f64,
+
+    /// Minimum gas used by a mined transaction.
+    #[serde(default = "default_gas_used_min")]
+    pub gas_used_min: u64,
+
+    /// Maximum gas used by a mined transaction.
+    #[serde(default = "default_gas_used_max")]
+    pub gas_used_max: u64,
+
+    /// Minimum time until a receipt is available (milliseconds).
+    #[serde(default = "default_latency_ms_min")]
+    pub latency_ms_min: u64,
+
+    /// Maximum time until a receipt is available (milliseconds).
+    #[serde(default = "default_latency_ms_max")]
+    pub latency_ms_max: u64,
+
+    /// How long to wait for a receipt before counting a transaction as
+    /// dropped (milliseconds).
+    #[serde(default = "default_receipt_timeout_ms")]
+    pub receipt_timeout_ms: u64,
+
+    /// Gas price (wei).
+    #[serde(default = "default_simulation_gas_price")]
+    pub gas_price: u64,
+
+    /// Native balance of every wallet (wei).
+    #[serde(default = "default_native_balance")]
+    pub native_balance: String,
+
+    /// DATA balance of every wallet (wei).
+    #[serde(default = "default_token_balance")]
+    pub token_balance: String,
+}
+
+fn default_simulation_start() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0)
+        .single()
+        .unwrap_or_default()
+}
+
+const fn default_simulation_days() -> u32 {
+    30
+}
+
+const fn default_revert_rate() -> f64 {
+    0.02
+}
+
+const fn default_drop_rate() -> f64 {
+    0.01
+}
+
+const fn default_gas_used_min() -> u64 {
+    60_000
+}
+
+const fn default_gas_used_max() -> u64 {
+    180_000
+}
+
+const fn default_latency_ms_min() -> u64 {
+    50
+}
+
+const fn default_latency_ms_max() -> u64 {
+    2_000
+}
+
+const fn default_receipt_timeout_ms() -> u64 {
+    30_000
+}
+
+const fn default_simulation_gas_price() -> u64 {
+    1_000_000_000 // 1 gwei
+}
+
+fn default_native_balance() -> String {
+    "1000000000000000000".into() // 1 ETH
+}
+
+fn default_token_balance() -> String {
+    "1000000000000000000000".into() // 1000 DATA
+}
+
+impl SimulationConfig {
+    /// Check the simulation settings.
+    fn check(&self) -> ConfigReport {
+        let mut report = ConfigReport::new();
+        for (key, rate) in [("revert_rate", self.revert_rate), ("drop_rate", self.drop_rate)] {
+            if !(0.0..=1.0).contains(&rate) {
+                report.error(key, "must be between 0.0 and 1.0");
+            }
+        }
+        if self.gas_used_min > self.gas_used_max {
+            report.error("gas_used_min", "must be <= gas_used_max");
+        }
+        if self.latency_ms_min > self.latency_ms_max {
+            report.error("latency_ms_min", "must be <= latency_ms_max");
+        }
+        check_wei(&mut report, "native_balance", &self.native_balance);
+        check_wei(&mut report, "token_balance", &self.token_balance);
+        report
+    }
+}
+
+impl Default for SimulationConfig {
     fn default() -> Self {
         Self {
-            max_consecutive_errors: default_max_errors(),
-            cooldown_secs: default_cooldown(),
-            max_actions_per_hour: default_max_actions(),
-            global_pause: false,
+            seed: 0,
+            start: default_simulation_start(),
+            days: default_simulation_days(),
+            revert_rate: default_revert_rate(),
+            drop_rate: default_drop_rate(),
+            gas_used_min: default_gas_used_min(),
+            gas_used_max: default_gas_used_max(),
+            latency_ms_min: default_latency_ms_min(),
+            latency_ms_max: default_latency_ms_max(),
+            receipt_timeout_ms: default_receipt_timeout_ms(),
+            gas_price: default_simulation_gas_price(),
+            native_balance: default_native_balance(),
+            token_balance: default_token_balance(),
         }
     }
 }
@@ -369,100 +3194,119 @@ impl Default for SafetyConfig {
 // ═══════════════════════════════════════════════════════════════════════════════
 
 /// Behavior profile configuration.
-#[derive(Debug, Clone, Deserialize, Serialize)]
+///
+/// Every field is optional: unset fields are inherited, see
+/// [`Settings::profile_catalog`].
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
 pub struct ProfileConfig {
+    /// Preset or profile this profile is based on.
+    #[serde(default)]
+    pub extends: Option<String>,
+
     /// Risk tolerance (0.0 to 1.0).
-    #[serde(default = "default_risk")]
-    pub risk_tolerance: f64,
+    #[serde(default)]
+    pub risk_tolerance: Option<f64>,
 
     /// Activity level (actions per hour).
-    #[serde(default = "default_activity")]
-    pub activity_level: f64,
+    #[serde(default)]
+    pub activity_level: Option<f64>,
 
     /// Patience factor (0.0 to 1.0).
-    #[serde(default = "default_patience")]
-    pub patience: f64,
+    #[serde(default)]
+    pub patience: Option<f64>,
 
     /// Base action interval in seconds.
-    #[serde(default = "default_interval")]
-    pub action_interval_secs: u64,
+    #[serde(default)]
+    pub action_interval_secs: Option<u64>,
 
     /// Interval jitter percentage (0-100).
-    #[serde(default = "default_jitter")]
-    pub action_interval_jitter_pct: u8,
+    #[serde(default)]
+    pub action_interval_jitter_pct: Option<u8>,
 
     /// Active hours start (UTC, 0-23).
-    #[serde(default = "default_hours_start")]
-    pub active_hours_start: u8,
+    #[serde(default)]
+    pub active_hours_start: Option<u8>,
 
     /// Active hours end (UTC, 0-23).
-    #[serde(default = "default_hours_end")]
-    pub active_hours_end: u8,
+    #[serde(default)]
+    pub active_hours_end: Option<u8>,
 
     /// Off-hours activity factor (0.0 to 1.0).
-    #[serde(default = "default_off_hours")]
-    pub off_hours_factor: f64,
+    #[serde(default)]
+    pub off_hours_factor: Option<f64>,
 
     /// AFK probability (0.0 to 1.0).
-    #[serde(default = "default_afk_prob")]
-    pub afk_probability: f64,
+    #[serde(default)]
+    pub afk_probability: Option<f64>,
 
     /// Minimum AFK duration in hours.
-    #[serde(default = "default_afk_min")]
-    pub afk_min_hours: u64,
+    #[serde(default)]
+    pub afk_min_hours: Option<u64>,
 
     /// Maximum AFK duration in hours.
-    #[serde(default = "default_afk_max")]
-    pub afk_max_hours: u64,
-}
-
-const fn default_risk() -> f64 { 0.5 }
-const fn default_activity() -> f64 { 5.0 }
-const fn default_patience() -> f64 { 0.5 }
-const fn default_interval() -> u64 { 3600 }
-const fn default_jitter() -> u8 { 50 }
-const fn default_hours_start() -> u8 { 8 }
-const fn default_hours_end() -> u8 { 22 }
-const fn default_off_hours() -> f64 { 0.3 }
-const fn default_afk_prob() -> f64 { 0.1 }
-const fn default_afk_min() -> u64 { 4 }
-const fn default_afk_max() -> u64 { 24 }
-
-impl Default for ProfileConfig {
-    fn default() -> Self {
-        Self {
-            risk_tolerance: default_risk(),
-            activity_level: default_activity(),
-            patience: default_patience(),
-            action_interval_secs: default_interval(),
-            action_interval_jitter_pct: default_jitter(),
-            active_hours_start: default_hours_start(),
-            active_hours_end: default_hours_end(),
-            off_hours_factor: default_off_hours(),
-            afk_probability: default_afk_prob(),
-            afk_min_hours: default_afk_min(),
-            afk_max_hours: default_afk_max(),
-        }
-    }
+    #[serde(default)]
+    pub afk_max_hours: Option<u64>,
+
+    /// Per-action cooldown overrides in seconds, keyed by action ID
+    /// (e.g. `"ghostnet.hashcrash_bet" = 1800`). 0 disables a cooldown.
+    /// Added to the overrides of the extended profile.
+    #[serde(default)]
+    pub action_cooldown_secs: HashMap<String, u64>,
+
+    /// Probability that an action starts a burst (0.0 to 1.0).
+    #[serde(default)]
+    pub burst_probability: Option<f64>,
+
+    /// Number of closely spaced actions in a burst.
+    #[serde(default)]
+    pub burst_size_range: Option<RangeInclusive<u32>>,
+
+    /// Spacing between actions in a burst in seconds.
+    #[serde(default)]
+    pub burst_spacing_secs: Option<u64>,
+
+    /// Default spend caps of the profile's wallets.
+    #[serde(default)]
+    pub budget: BudgetConfig,
 }
 
 impl ProfileConfig {
-    /// Convert to fleet-core BehaviorProfile.
+    /// Apply the fields set in this profile to `base`; set fields win.
+    ///
+    /// `extends` is not followed and the name is kept, see
+    /// [`Settings::profile_catalog`].
     #[must_use]
-    pub fn to_behavior_profile(&self, name: &str) -> fleet_core::profiles::BehaviorProfile {
-        fleet_core::profiles::BehaviorProfile {
-            name: name.to_string(),
-            risk_tolerance: self.risk_tolerance,
-            activity_level: self.activity_level,
-            patience: self.patience,
-            action_interval_secs: self.action_interval_secs,
-            action_interval_jitter_pct: self.action_interval_jitter_pct,
-            active_hours_start: self.active_hours_start,
-            active_hours_end: self.active_hours_end,
-            off_hours_factor: self.off_hours_factor,
-            afk_probability: self.afk_probability,
-            afk_min_hours: self.afk_min_hours,
-            afk_max_hours: self.afk_max_hours,
+    pub fn apply(&self, base: BehaviorProfile) -> BehaviorProfile {
+        let mut profile = base;
+        let cooldowns = self
+            .action_cooldown_secs
+            .iter()
+            .map(|(action, secs)| (action.as_str().into(), *secs));
+        profile.action_cooldown_secs.extend(cooldowns);
+
+        BehaviorProfile {
+            risk_tolerance: self.risk_tolerance.unwrap_or(profile.risk_tolerance),
+            activity_level: self.activity_level.unwrap_or(profile.activity_level),
+            patience: self.patience.unwrap_or(profile.patience),
+            action_interval_secs: self
+                .action_interval_secs
+                .unwrap_or(profile.action_interval_secs),
+            action_interval_jitter_pct: self
+                .action_interval_jitter_pct
+                .unwrap_or(profile.action_interval_jitter_pct),
+            active_hours_start: self.active_hours_start.unwrap_or(profile.active_hours_start),
+            active_hours_end: self.active_hours_end.unwrap_or(profile.active_hours_end),
+            off_hours_factor: self.off_hours_factor.unwrap_or(profile.off_hours_factor),
+            afk_probability: self.afk_probability.unwrap_or(profile.afk_probability),
+            afk_min_hours: self.afk_min_hours.unwrap_or(profile.afk_min_hours),
+            afk_max_hours: self.afk_max_hours.unwrap_or(profile.afk_max_hours),
+            burst_probability: self.burst_probability.unwrap_or(profile.burst_probability),
+            burst_size_range: self
+                .burst_size_range
+                .clone()
+                .unwrap_or_else(|| profile.burst_size_range.clone()),
+            burst_spacing_secs: self.burst_spacing_secs.unwrap_or(profile.burst_spacing_secs),
+            ..profile
         }
     }
 }
@@ -474,12 +3318,59 @@ impl ProfileConfig {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::error::FleetServiceError;
+
+    /// Paths of the errors of `report`.
+    fn error_paths(report: &ConfigReport) -> Vec<&str> {
+        report.errors().map(|issue| issue.path.as_str()).collect()
+    }
+
+    /// The errors of `settings`, one `path: message` per line.
+    fn errors(settings: &Settings) -> String {
+        let errors: Vec<_> = settings.check().errors().map(ToString::to_string).collect();
+        errors.join("\n")
+    }
 
     #[test]
     fn default_service_config() {
         let config = ServiceConfig::default();
         assert_eq!(config.name, "ghost-fleet");
         assert_eq!(config.tick_interval_ms, 1000);
+        assert_eq!(config.state_file_of(Some("alpha")), None);
+        assert_eq!(config.pnl_file_of(None), None);
+    }
+
+    #[test]
+    fn state_file_per_fleet() {
+        let config = ServiceConfig {
+            state_file: Some(PathBuf::from("/var/lib/fleet/state.json")),
+            ..ServiceConfig::default()
+        };
+        assert_eq!(
+            config.state_file_of(None),
+            Some(PathBuf::from("/var/lib/fleet/state.json"))
+        );
+        assert_eq!(
+            config.state_file_of(Some("alpha")),
+            Some(PathBuf::from("/var/lib/fleet/state.alpha.json"))
+        );
+
+        let config = ServiceConfig {
+            state_file: Some(PathBuf::from("state")),
+            state_save_interval_secs: 0,
+            ..ServiceConfig::default()
+        };
+        assert_eq!(config.state_file_of(Some("beta")), Some(PathBuf::from("state.beta")));
+        assert_eq!(config.check().errors().count(), 1);
+
+        let config = ServiceConfig {
+            pnl_file: Some(PathBuf::from("/var/lib/fleet/pnl.jsonl")),
+            ..ServiceConfig::default()
+        };
+        assert_eq!(
+            config.pnl_file_of(Some("alpha")),
+            Some(PathBuf::from("/var/lib/fleet/pnl.alpha.jsonl"))
+        );
     }
 
     #[test]
@@ -488,14 +3379,843 @@ mod tests {
         assert_eq!(config.max_consecutive_errors, 5);
         assert_eq!(config.cooldown_secs, 3600);
         assert!(!config.global_pause);
+        assert_eq!(config.transient_retries, 2);
+        assert_eq!(config.rate_limit_backoff_secs, 300);
+        assert_eq!(config.max_replacements, 3);
+        assert!(config.check().is_empty());
+    }
+
+    #[test]
+    fn replacement_limits_are_validated() {
+        let too_many = SafetyConfig {
+            max_replacements: 4,
+            ..SafetyConfig::default()
+        };
+        assert!(too_many.check().has_errors());
+
+        let underpriced = SafetyConfig {
+            replacement_fee_bump_pct: 5,
+            ..SafetyConfig::default()
+        };
+        assert!(underpriced.check().has_errors());
+    }
+
+    #[test]
+    fn plugin_timeouts() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let config: SafetyConfig = toml::from_str(
+            r"
+            execute_timeout_secs = 90
+
+            [plugin_timeouts.ghostnet]
+            execute_secs = 300
+
+            [plugin_timeouts.slow]
+            decide_secs = 0
+            ",
+        )?;
+        assert_eq!(config.decide_timeout_secs, 10);
+        assert_eq!(config.execute_timeout_secs, 90);
+        assert_eq!(config.plugin_timeouts["ghostnet"].execute_secs, Some(300));
+        assert_eq!(config.plugin_timeouts["ghostnet"].decide_secs, None);
+        assert_eq!(error_paths(&config.check()), ["plugin_timeouts.slow.decide_secs"]);
+        Ok(())
+    }
+
+    #[test]
+    fn profile_defaults_to_behavior_profile() {
+        let profile = ProfileConfig::default().apply(BehaviorProfile::new("test"));
+        assert_eq!(profile, BehaviorProfile::new("test"));
+    }
+
+    #[test]
+    fn profile_bursts() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let mut settings: Settings = toml::from_str(
+            r"
+            [profiles.test]
+            action_interval_secs = 600
+            burst_probability = 0.3
+            burst_size_range = { start = 2, end = 4 }
+            burst_spacing_secs = 90
+            ",
+        )?;
+        let catalog = settings.profile_catalog()?;
+        let profile = catalog.get("test").ok_or("test is resolved")?;
+        assert_eq!(profile.burst_size_range, 2..=4);
+        assert_eq!(profile.burst_spacing_secs, 90);
+
+        // Bursts must be faster than the usual pace
+        if let Some(test) = settings.profiles.get_mut("test") {
+            test.burst_spacing_secs = Some(600);
+        }
+        let error = settings.profile_catalog().err().map(|e| e.to_string()).unwrap_or_default();
+        assert!(error.contains("profiles.test.burst_spacing_secs"), "{error}");
+        Ok(())
+    }
+
+    #[test]
+    fn profile_cooldown_overrides() -> std::result::Result<(), toml::de::Error> {
+        let config: ProfileConfig = toml::from_str(
+            r#"
+            [action_cooldown_secs]
+            "ghostnet.hashcrash_bet" = 1800
+            "#,
+        )?;
+
+        let profile = config.apply(BehaviorProfile::new("test"));
+
+        assert_eq!(
+            profile.cooldown_for("ghostnet.hashcrash_bet", None),
+            Some(chrono::Duration::minutes(30))
+        );
+        Ok(())
+    }
+
+    const PROFILES: &str = r#"
+        [profiles.patient_whale]
+        extends = "whale"
+        patience = 1.0
+        budget = { daily_actions = 5 }
+
+        [profiles.night_whale]
+        extends = "patient_whale"
+        active_hours_start = 22
+        active_hours_end = 4
+
+        [profiles.whale]
+        extends = "whale"
+        risk_tolerance = 0.1
+    "#;
+
+    #[test]
+    fn profiles_extend_presets_and_profiles() -> std::result::Result<(), Box<dyn std::error::Error>>
+    {
+        let settings: Settings = toml::from_str(PROFILES)?;
+        let catalog = settings.profile_catalog()?;
+        assert_eq!(catalog.len(), 3);
+
+        // Overrides win, the rest comes from the preset
+        let whale = BehaviorProfile::whale();
+        let patient = catalog.get("patient_whale").ok_or("patient_whale is resolved")?;
+        assert_eq!(patient.name, "patient_whale");
+        assert!((patient.patience - 1.0).abs() < f64::EPSILON);
+        assert_eq!(patient.action_interval_secs, whale.action_interval_secs);
+        assert_eq!(patient.active_hours(), whale.active_hours());
+
+        // A profile named after a preset extends the preset, and shadows it
+        // for the profiles extending that name
+        let custom = catalog.get("whale").ok_or("whale is resolved")?;
+        assert!((custom.risk_tolerance - 0.1).abs() < f64::EPSILON);
+        assert!((custom.patience - whale.patience).abs() < f64::EPSILON);
+        assert!((patient.risk_tolerance - 0.1).abs() < f64::EPSILON);
+
+        // Through another profile
+        let night = catalog.get("night_whale").ok_or("night_whale is resolved")?;
+        assert!((night.patience - 1.0).abs() < f64::EPSILON);
+        assert_eq!((night.active_hours_start, night.active_hours_end), (22, 4));
+        assert!((night.risk_tolerance - 0.1).abs() < f64::EPSILON);
+
+        // Budgets are inherited as well
+        let wallet: WalletConfig = toml::from_str(
+            r#"
+            id = "night_1"
+            address = "0x1111111111111111111111111111111111111111"
+            profile = "night_whale"
+            "#,
+        )?;
+        assert_eq!(settings.wallet_budget(&wallet).daily.actions, Some(5));
+        Ok(())
+    }
+
+    const FLEETS: &str = r#"
+        [chain]
+        chain_id = 6343
+        rpc_url = "http://localhost:8545"
+
+        [profiles.steady]
+        action_interval_secs = 600
+
+        [[fleet.alpha.wallets]]
+        id = "w1"
+        address = "0x1111111111111111111111111111111111111111"
+        profile = "steady"
+
+        [[fleet.beta.wallets]]
+        id = "w1"
+        address = "0x2222222222222222222222222222222222222222"
+        profile = "eager"
+
+        [fleet.beta.profiles.eager]
+        action_interval_secs = 60
+
+        [fleet.beta.plugins]
+        enabled = []
+        selection = "weighted_random"
+    "#;
+
+    #[test]
+    fn fleets_have_their_own_wallets_profiles_and_plugins(
+    ) -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let settings: Settings = toml::from_str(FLEETS)?;
+        settings.validate()?;
+
+        let fleets = settings.fleet_settings();
+        let names: Vec<_> = fleets.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["alpha", "beta"]);
+        let (alpha, beta) = (&fleets[0].1, &fleets[1].1);
+        // Wallet ids repeat across fleets
+        assert_eq!(alpha.wallets[0].id, beta.wallets[0].id);
+        assert!(alpha.fleets.is_empty());
+        // Profiles add to the shared ones, plugins replace them
+        assert!(!alpha.profiles.contains_key("eager"));
+        assert!(beta.profiles.contains_key("eager") && beta.profiles.contains_key("steady"));
+        assert_eq!(alpha.plugins.selection, SelectionStrategy::default());
+        assert_eq!(beta.plugins.selection, SelectionStrategy::WeightedRandom);
+        Ok(())
+    }
+
+    #[test]
+    fn fleet_errors_name_the_fleet() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let fleets: Settings = toml::from_str(FLEETS)?;
+
+        // Within one fleet wallet ids are unique
+        let mut settings = fleets.clone();
+        let beta = settings.fleets.get_mut("beta").ok_or("beta is configured")?;
+        let mut twin = beta.wallets[0].clone();
+        twin.address = Address::repeat_byte(0x33);
+        beta.wallets.push(twin.clone());
+        let twice = errors(&settings);
+        assert_eq!(twice, "fleet.beta.wallets[1].id: 'w1' is used twice");
+
+        // Profiles of one fleet are not seen by another
+        let mut settings = fleets.clone();
+        let alpha = settings.fleets.get_mut("alpha").ok_or("alpha is configured")?;
+        alpha.wallets[0].profile = "eager".into();
+        let unknown = errors(&settings);
+        assert!(unknown.starts_with("fleet.alpha.wallets[0].profile: 'eager'"), "{unknown}");
+
+        // Addresses are unique across fleets
+        let mut settings = fleets.clone();
+        let beta = settings.fleets.get_mut("beta").ok_or("beta is configured")?;
+        beta.wallets[0].address = Address::repeat_byte(0x11);
+        let shared = errors(&settings);
+        assert_eq!(shared, "fleet.beta.wallets[0].address: is also a wallet of fleet 'alpha'");
+
+        let mut settings = fleets;
+        settings.wallets.push(twin);
+        let mixed = errors(&settings);
+        assert!(mixed.starts_with("wallets: ") && mixed.contains("not both"), "{mixed}");
+        Ok(())
+    }
+
+    #[test]
+    fn unresolvable_profiles_are_rejected() -> std::result::Result<(), toml::de::Error> {
+        let error = |config: &str| {
+            toml::from_str::<Settings>(config)
+                .map(|settings| settings.profile_catalog().err().map(|e| e.to_string()))
+        };
+
+        let unknown = error("[profiles.shark]\nextends = \"megalodon\"")?.unwrap_or_default();
+        assert!(unknown.contains("profiles.shark.extends  'megalodon'"), "{unknown}");
+
+        let circular = error(
+            "[profiles.a]\nextends = \"b\"\n[profiles.b]\nextends = \"c\"\n\
+             [profiles.c]\nextends = \"a\"",
+        )?
+        .unwrap_or_default();
+        assert!(circular.contains("circular: a -> b -> c -> a"), "{circular}");
+
+        // Validation sees the merged profile and names the field
+        let invalid = error("[profiles.risky]\nextends = \"degen\"\nafk_min_hours = 100")?
+            .unwrap_or_default();
+        assert!(invalid.contains("profiles.risky.afk_hours"), "{invalid}");
+        Ok(())
+    }
+
+    #[test]
+    fn wallet_budget_overrides_profile() -> std::result::Result<(), toml::de::Error> {
+        let profile: BudgetConfig = toml::from_str(
+            r#"
+            daily_actions = 10
+            daily_data_wei = "5000"
+            "#,
+        )?;
+        let wallet: BudgetConfig = toml::from_str(r#"daily_data_wei = "1000""#)?;
+
+        let caps = wallet.or(&profile).to_caps();
+        assert_eq!(caps.daily.actions, Some(10));
+        assert_eq!(caps.daily.data, Some(U256::from(1000)));
+        assert_eq!(caps.lifetime, SpendLimit::UNLIMITED);
+
+        let invalid = BudgetConfig {
+            lifetime_gas_wei: Some("1 ether".into()),
+            ..BudgetConfig::default()
+        };
+        assert_eq!(error_paths(&invalid.check()), ["lifetime_gas_wei"]);
+
+        // Daily caps above the lifetime ones are only warned about
+        let pointless = BudgetConfig {
+            daily_actions: Some(100),
+            lifetime_actions: Some(10),
+            ..BudgetConfig::default()
+        };
+        let report = pointless.check();
+        assert!(!report.has_errors());
+        assert_eq!(report.issues()[0].path, "daily_actions");
+        Ok(())
+    }
+
+    #[test]
+    fn plugin_selection_settings() -> std::result::Result<(), toml::de::Error> {
+        let config: PluginsConfig = toml::from_str(
+            r#"
+            enabled = ["ghostnet", "swap"]
+            selection = "weighted_random"
+            priorities = { swap = 50 }
+            "#,
+        )?;
+
+        assert_eq!(config.selection, SelectionStrategy::WeightedRandom);
+        assert_eq!(config.priority("swap"), 50);
+        assert_eq!(config.priority("ghostnet"), DEFAULT_PRIORITY);
+        assert_eq!(
+            PluginsConfig::default().selection,
+            SelectionStrategy::HighestPriority
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn wallet_groups() -> std::result::Result<(), toml::de::Error> {
+        let wallet: WalletConfig = toml::from_str(
+            r#"
+            id = "whale_1"
+            address = "0x1111111111111111111111111111111111111111"
+            profile = "whale"
+            group = "funded_by_a"
+            "#,
+        )?;
+        let group: GroupConfig = toml::from_str("max_actions = 3\nwindow_secs = 300")?;
+
+        assert_eq!(wallet.group.as_deref(), Some("funded_by_a"));
+        let limit = group.to_limit();
+        assert_eq!(limit.max_actions, 3);
+        assert_eq!(limit.window, chrono::Duration::minutes(5));
+        Ok(())
+    }
+
+    #[test]
+    fn rpc_endpoints() -> std::result::Result<(), toml::de::Error> {
+        let chain: ChainConfig = toml::from_str(
+            r#"
+            chain_id = 6343
+            rpc_url = "https://carrot.megaeth.com/rpc"
+            endpoint_assignment = "weighted"
+
+            [[endpoints]]
+            name = "backup"
+            url = "https://backup.example/rpc"
+            weight = 3
+            "#,
+        )?;
+        assert_eq!(chain.endpoint_assignment, AssignmentStrategy::Weighted);
+        assert_eq!(chain.endpoints[0].weight, 3);
+        assert_eq!(chain.endpoint_failure_threshold, 3);
+        assert!(chain.check_endpoints().is_empty());
+
+        for invalid in [
+            "name = \"primary\"\nurl = \"https://a.example\"",
+            "name = \"a\"\nurl = \"https://a.example\"\nweight = 0",
+            "name = \"\"\nurl = \"https://a.example\"",
+        ] {
+            let endpoint: EndpointConfig = toml::from_str(invalid)?;
+            let chain = ChainConfig {
+                endpoints: vec![endpoint],
+                ..chain.clone()
+            };
+            assert_eq!(error_paths(&chain.check_endpoints()), ["endpoints[0]"], "{invalid}");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn cold_start_settings() -> std::result::Result<(), toml::de::Error> {
+        let cold_start: ColdStartConfig = toml::from_str("ramp_window_secs = 600")?;
+        let settings = cold_start.to_settings();
+
+        assert!(cold_start.enabled);
+        assert_eq!(settings.window, chrono::Duration::minutes(10));
+        assert_eq!((settings.max_per_minute, settings.reads_per_sec), (20, 5));
+        assert!(cold_start.check().is_empty());
+
+        for (invalid, key) in [
+            ("ramp_window_secs = 59", "ramp_window_secs"),
+            ("ramp_window_secs = 86401", "ramp_window_secs"),
+            ("max_actions_per_minute = 0", "max_actions_per_minute"),
+        ] {
+            let cold_start: ColdStartConfig = toml::from_str(invalid)?;
+            assert_eq!(error_paths(&cold_start.check()), [key], "{invalid} should be rejected");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn leader_settings() -> std::result::Result<(), toml::de::Error> {
+        let leader: LeaderConfig =
+            toml::from_str("enabled = true\nnode_id = \"a\"\nlease_file = \"fleet.lease\"")?;
+        assert_eq!((leader.lease_ttl_secs, leader.heartbeat_interval_secs), (30, 10));
+        assert_eq!(leader.node_id(), "a");
+        assert!(leader.check().is_empty());
+        assert!(LeaderConfig::default().node_id().starts_with("ghost-fleet-"));
+
+        for (invalid, key) in [
+            ("enabled = true", "lease_file"),
+            ("node_id = \"\"", "node_id"),
+            ("heartbeat_interval_secs = 0", "heartbeat_interval_secs"),
+            ("heartbeat_interval_secs = 30", "heartbeat_interval_secs"),
+        ] {
+            let leader: LeaderConfig = toml::from_str(invalid)?;
+            assert_eq!(error_paths(&leader.check()), [key], "{invalid} should be rejected");
+        }
+
+        // A standby takes over from the state file
+        let settings: Settings = toml::from_str("[leader]\nenabled = true\nlease_file = \"l\"")?;
+        assert!(error_paths(&settings.check()).contains(&"service.state_file"));
+        Ok(())
+    }
+
+    #[test]
+    fn diversity_settings() -> std::result::Result<(), toml::de::Error> {
+        let diversity: DiversityConfig = toml::from_str(
+            "seed = 7\nmin_hourly_coverage = 0.3\nrisk_buckets = [0.2, 0.5, 0.3]\n\
+             correlation_bucket_secs = 300",
+        )?;
+        let targets = diversity.to_targets();
+        let correlation = diversity.to_correlation_settings();
+
+        assert!(diversity.check().is_empty());
+        assert_eq!(targets.risk_buckets, [0.2, 0.5, 0.3]);
+        assert!((targets.max_shared_interval_fraction - 1.0).abs() < f64::EPSILON);
+        assert_eq!(correlation.bucket, chrono::Duration::minutes(5));
+        assert_eq!(correlation.window, chrono::Duration::hours(6));
+
+        for (invalid, key) in [
+            ("min_hourly_coverage = 1.5", "min_hourly_coverage"),
+            ("max_shared_interval_fraction = -0.1", "max_shared_interval_fraction"),
+            ("risk_buckets = [0.5, -0.5]", "risk_buckets"),
+            ("risk_buckets = [0.0, 0.0]", "risk_buckets"),
+            ("correlation_bucket_secs = 0", "correlation_bucket_secs"),
+            ("correlation_window_secs = 60", "correlation_window_secs"),
+            ("correlation_threshold = 0.0", "correlation_threshold"),
+        ] {
+            let diversity: DiversityConfig = toml::from_str(invalid)?;
+            assert_eq!(error_paths(&diversity.check()), [key], "{invalid} should be rejected");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn plugin_health_settings() -> std::result::Result<(), toml::de::Error> {
+        let health: PluginHealthConfig =
+            toml::from_str("min_calls = 20\nprobe_interval_secs = 60")?;
+        let settings = health.to_settings();
+
+        assert!(health.check().is_empty());
+        assert!(settings.auto_disable);
+        assert_eq!(settings.min_calls, 20);
+        assert_eq!(settings.window, std::time::Duration::from_secs(600));
+        assert_eq!(settings.probe_interval, std::time::Duration::from_secs(60));
+
+        for (invalid, key) in [
+            ("window_secs = 0", "window_secs"),
+            ("min_calls = 0", "min_calls"),
+            ("max_error_rate = 1.5", "max_error_rate"),
+            ("probe_interval_secs = 0", "probe_interval_secs"),
+        ] {
+            let health: PluginHealthConfig = toml::from_str(invalid)?;
+            assert_eq!(error_paths(&health.check()), [key], "{invalid} should be rejected");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn warmup_settings() -> std::result::Result<(), toml::de::Error> {
+        let warmup: WarmupConfig = toml::from_str("enabled = true\nmax_days = 10")?;
+        let settings = warmup.to_settings();
+
+        assert!(settings.enabled);
+        assert_eq!((settings.min_days, settings.max_days), (3, 10));
+        assert!((settings.initial_factor - 0.1).abs() < f64::EPSILON);
+        assert!(warmup.check().is_empty());
+
+        for (invalid, key) in [
+            ("min_days = 0", "min_days"),
+            ("min_days = 8", "min_days"),
+            ("initial_factor = 0.0", "initial_factor"),
+        ] {
+            let warmup: WarmupConfig = toml::from_str(invalid)?;
+            assert_eq!(error_paths(&warmup.check()), [key], "{invalid} should be rejected");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn review_settings() -> std::result::Result<(), toml::de::Error> {
+        let review: ReviewConfig = toml::from_str("")?;
+        assert_eq!((review.mode, review.delay_secs), (ReviewMode::Off, 300));
+
+        let review: ReviewConfig =
+            toml::from_str("mode = \"strict\"\njournal = \"plans.jsonl\"")?;
+        assert_eq!(review.mode, ReviewMode::Strict);
+        assert_eq!(review.journal, Some(PathBuf::from("plans.jsonl")));
+        assert!(review.check().is_empty());
+
+        let review: ReviewConfig = toml::from_str("mode = \"delay\"\ndelay_secs = 0")?;
+        assert_eq!(error_paths(&review.check()), ["delay_secs"]);
+        Ok(())
+    }
+
+    #[test]
+    fn report_settings() -> std::result::Result<(), toml::de::Error> {
+        let report: ReportConfig = toml::from_str("")?;
+        assert!(!report.enabled);
+        assert_eq!(report.dir, PathBuf::from("reports"));
+        assert_eq!((report.webhook_retries, report.webhook_max_bytes), (3, 256 * 1024));
+        assert!(report.check().is_empty());
+
+        let report: ReportConfig =
+            toml::from_str("hour_utc = 24\nwebhook_url = \"hooks.example\"\nwebhook_max_bytes = 0")?;
+        assert_eq!(
+            error_paths(&report.check()),
+            ["hour_utc", "webhook_url", "webhook_max_bytes"]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn retirement_settings() -> std::result::Result<(), toml::de::Error> {
+        let retirement: RetirementConfig = toml::from_str("dust_data_wei = \"5\"")?;
+        let settings = retirement.to_settings();
+        assert_eq!(settings.dust_native, RetirementSettings::default().dust_native);
+        assert_eq!(settings.dust_token, U256::from(5));
+        assert!(retirement.replace_with_standby);
+        assert!(retirement.check().is_empty());
+
+        for (invalid, key) in [
+            ("dust_native_wei = \"0\"", "dust_native_wei"),
+            ("dust_data_wei = \"0.5\"", "dust_data_wei"),
+        ] {
+            let retirement: RetirementConfig = toml::from_str(invalid)?;
+            assert_eq!(error_paths(&retirement.check()), [key], "{invalid} should be rejected");
+        }
+
+        let wallet = r#"
+            id = "whale_1"
+            address = "0x1111111111111111111111111111111111111111"
+            profile = "whale"
+        "#;
+        let valid: WalletConfig = toml::from_str(&format!(
+            "{wallet}retiring = true\nsweep_to = \"0x2222222222222222222222222222222222222222\""
+        ))?;
+        let mut settings: Settings = toml::from_str("[profiles.whale]")?;
+        settings.wallets = vec![valid];
+        assert!(settings.check_wallets().is_empty());
+        for (invalid, key) in [
+            ("retiring = true\nstandby = true", "[0].standby"),
+            ("sweep_to = \"0x1111111111111111111111111111111111111111\"", "[0].sweep_to"),
+        ] {
+            settings.wallets = vec![toml::from_str(&format!("{wallet}{invalid}"))?];
+            assert_eq!(error_paths(&settings.check_wallets()), [key]);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn key_sources() -> std::result::Result<(), toml::de::Error> {
+        let wallet: WalletConfig = toml::from_str(
+            r#"
+            id = "whale_1"
+            address = "0x1111111111111111111111111111111111111111"
+            profile = "whale"
+            key_source = { type = "mnemonic", mnemonic = "fleet", index = 3 }
+            "#,
+        )?;
+        let mnemonic: MnemonicConfig = toml::from_str(r#"phrase_env = "FLEET_MNEMONIC""#)?;
+
+        assert!(matches!(
+            &wallet.key_source,
+            Some(KeySource::Mnemonic { mnemonic, index: 3 }) if mnemonic == "fleet"
+        ));
+        assert_eq!(mnemonic.derivation_path, "m/44'/60'/0'/0");
+
+        let keystore: KeySource =
+            toml::from_str("type = \"keystore\"\npath = \"keys/whale_1.json\"")?;
+        assert!(matches!(keystore, KeySource::Keystore { password_env: None, .. }));
+        let remote: KeySource = toml::from_str("type = \"remote\"\nurl = \"http://signer:9000\"")?;
+        assert!(matches!(remote, KeySource::Remote { timeout_secs: 10, .. }));
+        Ok(())
+    }
+
+    const CHAINS: &str = r#"
+        [service]
+        default_chain = "testnet"
+
+        [chains.testnet]
+        chain_id = 6343
+        rpc_url = "https://carrot.megaeth.com/rpc"
+        ws_url = "wss://carrot.megaeth.com/ws"
+
+        [chains.testnet.ghostnet]
+        ghost_core = "0x00000000000000000000000000000000000000c0"
+        hash_crash = "0x00000000000000000000000000000000000000c1"
+        arcade_core = "0x00000000000000000000000000000000000000c2"
+
+        [chains.mainnet]
+        chain_id = 4326
+        rpc_url = "https://mainnet.megaeth.com/rpc"
+
+        [plugins]
+        enabled = ["ghostnet"]
+
+        [plugins.ghostnet]
+    "#;
+
+    #[test]
+    fn chain_profile_selection() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let mut settings: Settings = toml::from_str(CHAINS)?;
+        settings.select_chain(None)?;
+        assert_eq!(settings.chain.chain_id, 6343);
+        assert_eq!(settings.chain.ws_url.as_deref(), Some("wss://carrot.megaeth.com/ws"));
+        assert_eq!(settings.chain_key(), "chains.testnet");
+
+        // The CLI overrides the default
+        let mut settings: Settings = toml::from_str(CHAINS)?;
+        settings.select_chain(Some("mainnet"))?;
+        assert_eq!(settings.chain.chain_id, 4326);
+        assert!(settings.select_chain(Some("devnet")).is_err());
+
+        let mut settings: Settings = toml::from_str(CHAINS)?;
+        settings.service.default_chain = None;
+        assert!(settings.select_chain(None).is_err());
+
+        // A single [chain] needs no selection, but cannot be combined with profiles
+        let single = "[chain]\nchain_id = 31337\nrpc_url = \"http://localhost:8545\"";
+        let mut settings: Settings = toml::from_str(single)?;
+        settings.select_chain(None)?;
+        assert_eq!(settings.chain_key(), "chain");
+        assert!(settings.select_chain(Some("testnet")).is_err());
+
+        let mut settings: Settings = toml::from_str(&format!("{single}\n{CHAINS}"))?;
+        assert!(settings.select_chain(None).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn chain_validation() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let mut settings: Settings = toml::from_str(CHAINS)?;
+        settings.select_chain(None)?;
+
+        // Testnet lacks the DATA token address
+        let error = settings.validate().err().map(|e| e.to_string()).unwrap_or_default();
+        assert!(error.contains("chains.testnet.ghostnet.data_token"), "{error}");
+        assert!(settings.ghostnet_config().is_none());
+
+        settings.chain.ghostnet.data_token = Some(Address::with_last_byte(0xDA));
+        settings.validate()?;
+        assert!(settings.ghostnet_config().is_some_and(|c| c.chain_id == 6343));
+
+        // Bet sizing after a losing streak is a fraction of the usual size
+        if let Some(ghostnet) = settings.plugins.ghostnet.as_mut() {
+            ghostnet.hashcrash_loss_streak_bet_pct = 1.5;
+        }
+        assert!(settings.validate().is_err());
+
+        // Boost spend is a wei amount
+        if let Some(ghostnet) = settings.plugins.ghostnet.as_mut() {
+            ghostnet.hashcrash_loss_streak_bet_pct = 0.5;
+            ghostnet.max_boost_spend = "10 DATA".into();
+        }
+        assert!(settings.validate().is_err());
+        if let Some(ghostnet) = settings.plugins.ghostnet.as_mut() {
+            ghostnet.max_boost_spend = "10000000000000000000".into();
+        }
+        let config = settings.ghostnet_config();
+        assert!(config.is_some_and(|c| c.behavior.max_boost_spend == 10_000_000_000_000_000_000));
+
+        // Arcade games are filtered by ID
+        if let Some(ghostnet) = settings.plugins.ghostnet.as_mut() {
+            ghostnet.arcade_games = toml::from_str("allow = [1, 2]\ndeny = [2]")?;
+        }
+        let config = settings.ghostnet_config();
+        assert!(config.is_some_and(|c| c.behavior.plays_arcade
+            && c.arcade_games.allows(1)
+            && !c.arcade_games.allows(2)));
+
+        // Ladders have at least one step
+        if let Some(ghostnet) = settings.plugins.ghostnet.as_mut() {
+            ghostnet.extract_strategy = toml::from_str("type = \"ladder\"\nsteps = 0")?;
+        }
+        assert!(settings.validate().is_err());
+        if let Some(ghostnet) = settings.plugins.ghostnet.as_mut() {
+            ghostnet.extract_strategy = ExtractStrategy::Ladder { steps: 3 };
+        }
+        let config = settings.ghostnet_config();
+        assert!(config.is_some_and(|c| c.behavior.extract_strategy
+            == ExtractStrategy::Ladder { steps: 3 }));
+
+        // Gas caps leave room for a transaction
+        if let Some(ghostnet) = settings.plugins.ghostnet.as_mut() {
+            ghostnet.gas = toml::from_str("action_max_gas = { \"ghostnet.extract\" = 0 }")?;
+        }
+        assert!(settings.validate().is_err());
+        if let Some(ghostnet) = settings.plugins.ghostnet.as_mut() {
+            ghostnet.gas = toml::from_str("margin_bps = 1000\nmax_gas = 2000000")?;
+        }
+        let config = settings.ghostnet_config();
+        assert!(config.is_some_and(|c| c.gas.margin_bps == 1000 && c.gas.max_gas == 2_000_000));
+
+        settings.check_chain_id(6343)?;
+        assert!(matches!(
+            settings.check_chain_id(4326),
+            Err(FleetServiceError::Config(ConfigError::ChainIdMismatch {
+                expected: 6343,
+                actual: 4326,
+                ..
+            }))
+        ));
+        Ok(())
+    }
+
+    const BROKEN: &str = r#"
+        [chain]
+        chain_id = 0
+        rpc_url = "http://localhost:8545"
+
+        [chain.ghostnet]
+        ghost_core = "0x00000000000000000000000000000000000000c0"
+        hash_crash = "0x00000000000000000000000000000000000000c0"
+        arcade_core = "0x00000000000000000000000000000000000000c2"
+
+        [plugins]
+        enabled = ["ghostnet", "swap"]
+
+        [plugins.ghostnet]
+        max_boost_reward_share = 1.5
+        data_reserve = "lots"
+
+        [profiles.steady]
+        extends = "grinder"
+
+        [mnemonics.fleet]
+        phrase_env = "FLEET_MNEMONIC"
+
+        [[wallets]]
+        id = "w0"
+        address = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"
+        profile = "steady"
+
+        [[wallets]]
+        id = "w1"
+        address = "0xFB6916095ca1df60bB79Ce92cE3Ea74c37c5d359"
+        profile = "steady"
+
+        [[wallets]]
+        id = "w1"
+        address = "0x3333333333333333333333333333333333333333"
+        profile = "shark"
+
+        [[wallets]]
+        id = "w3"
+        address = "0x4444444444444444444444444444444444444444"
+        profile = "steady"
+        key_source = { type = "mnemonic", mnemonic = "treasury", index = 0 }
+    "#;
+
+    #[test]
+    fn validation_reports_every_issue_by_path()
+    -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("broken.toml");
+        fs::write(&path, BROKEN)?;
+        let mut settings = Settings::load(&path)?;
+        settings.select_chain(None)?;
+
+        let report = settings.check();
+        assert_eq!(
+            error_paths(&report),
+            [
+                "wallets[1].address",
+                "chain.chain_id",
+                "chain.ghostnet.data_token",
+                "plugins.ghostnet.data_reserve",
+                "chain.ghostnet.hash_crash",
+                "plugins.ghostnet.max_boost_reward_share",
+                "wallets[2].id",
+                "wallets[2].profile",
+                "wallets[3].key_source.mnemonic",
+            ]
+        );
+        // The checksum mismatch suggests the right spelling
+        assert!(report.issues()[0].message.ends_with("0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359"));
+
+        // Warnings do not fail validation on their own
+        let warnings: Vec<_> = report.warnings().map(|issue| issue.path.as_str()).collect();
+        assert_eq!(warnings, ["safety.budget", "plugins.enabled[1]"]);
+
+        let error = settings.validate().err().map(|e| e.to_string()).unwrap_or_default();
+        let line = error.lines().find(|line| line.contains("wallets[3].key_source.mnemonic"));
+        assert!(line.is_some_and(|line| line.starts_with("error")), "{error}");
+        Ok(())
+    }
+
+    #[test]
+    fn manifest_overrides_inline_addresses()
+    -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let dir = tempfile::tempdir()?;
+        let manifest = dir.path().join("testnet.json");
+        fs::write(
+            &manifest,
+            r#"{
+                "chainId": 6343,
+                "hashCrash": "0x00000000000000000000000000000000000000d1",
+                "dataToken": "0x00000000000000000000000000000000000000da",
+                "arcadeCore": "",
+                "deadPool": "0x00000000000000000000000000000000000000ee"
+            }"#,
+        )?;
+
+        let mut settings: Settings = toml::from_str(CHAINS)?;
+        if let Some(testnet) = settings.chains.get_mut("testnet") {
+            testnet.addresses_file = Some(manifest.clone());
+        }
+        settings.select_chain(None)?;
+
+        let addresses = settings.chain.ghostnet;
+        // Set in the manifest
+        assert_eq!(addresses.hash_crash, Some(Address::with_last_byte(0xD1)));
+        assert_eq!(addresses.data_token, Some(Address::with_last_byte(0xDA)));
+        // Only inline, or empty in the manifest
+        assert_eq!(addresses.ghost_core, Some(Address::with_last_byte(0xC0)));
+        assert_eq!(addresses.arcade_core, Some(Address::with_last_byte(0xC2)));
+        settings.validate()?;
+
+        // A manifest of another chain is rejected
+        let mut settings: Settings = toml::from_str(CHAINS)?;
+        if let Some(mainnet) = settings.chains.get_mut("mainnet") {
+            mainnet.addresses_file = Some(manifest);
+        }
+        assert!(settings.select_chain(Some("mainnet")).is_err());
+        Ok(())
     }
 
     #[test]
-    fn profile_to_behavior_profile() {
-        let config = ProfileConfig::default();
-        let profile = config.to_behavior_profile("test");
-        
-        assert_eq!(profile.name, "test");
-        assert!((profile.risk_tolerance - 0.5).abs() < f64::EPSILON);
+    fn simulation_defaults_fill_gaps() -> std::result::Result<(), toml::de::Error> {
+        let config: SimulationConfig = toml::from_str("seed = 42\ndays = 7")?;
+
+        assert_eq!(config.seed, 42);
+        assert_eq!(config.days, 7);
+        assert_eq!(config.start, default_simulation_start());
+        assert_eq!(config.gas_price, default_simulation_gas_price());
+        assert!(config.native_balance.parse::<U256>().is_ok());
+        Ok(())
     }
 }
@@ -0,0 +1,1201 @@
+//! Control socket for managing a running fleet.
+//!
+//! `ghost-fleet ctl` talks to the [`FleetService`](crate::service::FleetService)
+//! of a running fleet over a local TCP socket. The protocol is one JSON
+//! [`ControlRequest`] per line, answered by one JSON [`ControlResponse`] per
+//! line:
+//!
+//! ```text
+//! > {"token":"secret","command":"pause","wallet_id":"whale_1"}
+//! < {"done":"Paused wallet whale_1"}
+//! ```
+//!
+//! The server only checks the token and forwards commands; the service
+//! handles them on its main loop, between ticks, so a command never races a
+//! wallet that is being processed.
+//!
+//! # Commands
+//!
+//! | Command | Effect |
+//! |---------|--------|
+//! | `status` | Fleet snapshot and per-wallet schedule |
+//! | `pause` / `resume` | Take a wallet out of scheduling, or put it back |
+//! | `reset_breaker` | Reset a wallet's circuit breaker, or all breakers |
+//! | `trigger` | Run one action of a wallet now |
+//! | `reload_profiles` | Re-read `[profiles]` from the config file |
+//! | `plans` | Planned batches waiting for [review](crate::review) |
+//! | `approve` | Run a planned batch on the next tick |
+//! | `reject` | Drop a planned batch, or one wallet's action in it |
+//! | `retire` | Wind a wallet down, see [`Retirement`](fleet_core::wallet::Retirement) |
+//! | `report` | [Activity report](crate::report) of the day so far |
+//! | `plugin` | Switch a plugin off or on, or back to [automatic](fleet_core::plugins::PluginHealth) |
+
+use std::fmt;
+
+use alloy::primitives::Address;
+use chrono::{DateTime, Utc};
+use fleet_core::metrics::FleetSnapshot;
+use fleet_core::plugins::{ActionResult, PluginOverride, PluginState};
+use fleet_core::safety::DegradationTier;
+use fleet_core::wallet::RetirementStage;
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{mpsc, oneshot, watch};
+use tracing::{debug, info, warn};
+
+use crate::config::ControlConfig;
+use crate::error::{FleetServiceError, Result};
+use crate::report::DailyReport;
+use crate::review::PlannedBatch;
+
+/// Commands queued for the service; the service stops reading once the
+/// queue is full, so a flood of requests only ever waits.
+const QUEUE_CAPACITY: usize = 16;
+
+/// Longest request line accepted, in bytes.
+const MAX_REQUEST_LEN: u64 = 64 * 1024;
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// PROTOCOL
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// A command for the running fleet.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum ControlCommand {
+    /// Report the fleet snapshot and every wallet's schedule.
+    Status,
+
+    /// Stop scheduling a wallet until it is resumed.
+    Pause {
+        /// Wallet to pause.
+        wallet_id: String,
+    },
+
+    /// Schedule a paused wallet again, right away.
+    Resume {
+        /// Wallet to resume.
+        wallet_id: String,
+    },
+
+    /// Reset a wallet's circuit breaker, or every breaker (including the
+    /// global one) if no wallet is given.
+    ResetBreaker {
+        /// Wallet to reset, `None` for all.
+        #[serde(default)]
+        wallet_id: Option<String>,
+    },
+
+    /// Run one action of a wallet now, whatever its schedule.
+    ///
+    /// Breakers, rate limits and budgets still apply; cooldowns do not.
+    Trigger {
+        /// Wallet that acts.
+        wallet_id: String,
+        /// Action to run, e.g. `ghostnet.jack_in`.
+        action_id: String,
+    },
+
+    /// Re-read the behavior profiles from the config file.
+    ReloadProfiles,
+
+    /// List the planned batches waiting for review.
+    Plans,
+
+    /// Run a planned batch on the next tick, without waiting out its review
+    /// delay.
+    Approve {
+        /// Batch to approve.
+        batch_id: u64,
+    },
+
+    /// Drop one wallet's action from a planned batch, or the whole batch if
+    /// no wallet is given.
+    Reject {
+        /// Batch of the actions.
+        batch_id: u64,
+        /// Wallet whose action to drop, `None` for all.
+        #[serde(default)]
+        wallet_id: Option<String>,
+    },
+
+    /// Start retiring a wallet: it only exits, then sweeps and is retired.
+    Retire {
+        /// Wallet to retire.
+        wallet_id: String,
+        /// Address to sweep its balances to, instead of its configured
+        /// `sweep_to`.
+        #[serde(default)]
+        sweep_to: Option<Address>,
+    },
+
+    /// Report what the wallets did so far today.
+    Report {
+        /// Wallet to report on, `None` for all.
+        #[serde(default)]
+        wallet_id: Option<String>,
+    },
+
+    /// Disable or enable a plugin whatever its error rate, or let its error
+    /// rate decide again.
+    Plugin {
+        /// Plugin to switch, e.g. `ghostnet`.
+        plugin_id: String,
+        /// What to switch it to.
+        switch: PluginSwitch,
+    },
+}
+
+/// What the `plugin` command switches a plugin to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PluginSwitch {
+    /// Enabled, however often it fails.
+    Enable,
+
+    /// Disabled, without probes.
+    Disable,
+
+    /// Disabled while it fails too often, as by default.
+    Auto,
+}
+
+impl PluginSwitch {
+    /// The override this switch sets, `None` for none.
+    #[must_use]
+    pub const fn to_override(self) -> Option<PluginOverride> {
+        match self {
+            Self::Enable => Some(PluginOverride::Enabled),
+            Self::Disable => Some(PluginOverride::Disabled),
+            Self::Auto => None,
+        }
+    }
+}
+
+/// A command with the token that authorizes it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ControlRequest {
+    /// The shared `control.token`, if one is configured.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+
+    /// Command to run.
+    #[serde(flatten)]
+    pub command: ControlCommand,
+}
+
+/// Answer to a [`ControlRequest`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ControlResponse {
+    /// Answer to [`ControlCommand::Status`].
+    Status(Box<FleetStatus>),
+
+    /// The command was carried out.
+    Done(String),
+
+    /// Outcome of a [`ControlCommand::Trigger`]ed action.
+    Triggered(Box<ActionResult>),
+
+    /// Answer to [`ControlCommand::Plans`].
+    Plans(Vec<PlannedBatch>),
+
+    /// Answer to [`ControlCommand::Report`].
+    Report(Box<DailyReport>),
+
+    /// The command was rejected or failed.
+    Failed(String),
+}
+
+/// State of the fleet, as reported by [`ControlCommand::Status`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FleetStatus {
+    /// `safety.global_pause` is set.
+    pub global_pause: bool,
+
+    /// End of a global breaker trip, if one is open.
+    pub global_breaker_until: Option<DateTime<Utc>>,
+
+    /// Fleet-wide metrics.
+    pub snapshot: FleetSnapshot,
+
+    /// Every wallet, in ID order.
+    pub wallets: Vec<WalletStatus>,
+}
+
+/// Schedule of one wallet in a [`FleetStatus`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalletStatus {
+    /// Wallet ID.
+    pub id: String,
+
+    /// Behavior profile name.
+    pub profile: String,
+
+    /// Paused through the control socket.
+    pub paused: bool,
+
+    /// Circuit breaker tripped.
+    pub tripped: bool,
+
+    /// End of the wallet's AFK period, if it is away.
+    pub afk_until: Option<DateTime<Utc>>,
+
+    /// When the wallet considers its next action.
+    pub next_action: DateTime<Utc>,
+
+    /// Errors since the last success.
+    pub consecutive_errors: u32,
+
+    /// Stage of the wallet's retirement, if it is retiring or retired.
+    #[serde(default)]
+    pub retirement: Option<RetirementStage>,
+}
+
+impl fmt::Display for FleetStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let snapshot = &self.snapshot;
+        if let Some(timestamp) = snapshot.timestamp {
+            writeln!(f, "Fleet status at {timestamp}")?;
+        }
+        writeln!(
+            f,
+            "Wallets: {} active, {} tripped, {} AFK, {} over budget",
+            snapshot.active_wallets,
+            snapshot.tripped_wallets,
+            snapshot.afk_wallets,
+            snapshot.budget_exhausted_wallets
+        )?;
+        writeln!(
+            f,
+            "Actions: {} total, {} succeeded, {} failed",
+            snapshot.total_actions, snapshot.successful_actions, snapshot.failed_actions
+        )?;
+        writeln!(f, "Gas spent: {} wei", snapshot.total_gas_cost_wei)?;
+        let pnl = &snapshot.pnl;
+        writeln!(
+            f,
+            "DATA: {} wei in, {} wei out, net {} wei ({} wei today), gas {} wei",
+            pnl.lifetime.data_in,
+            pnl.lifetime.data_out,
+            pnl.lifetime.net_data(),
+            pnl.today.net_data(),
+            pnl.lifetime.gas_wei
+        )?;
+        if pnl.closed_positions > 0 {
+            writeln!(
+                f,
+                "Realized: {} wei over {} closed positions",
+                pnl.realized_data, pnl.closed_positions
+            )?;
+        }
+        if self.global_pause {
+            writeln!(f, "Global pause is ON")?;
+        }
+        if let Some(until) = self.global_breaker_until {
+            writeln!(f, "Global breaker open until {until}")?;
+        }
+        let degradation = &snapshot.degradation;
+        if degradation.tier != DegradationTier::Normal {
+            write!(f, "RPC degraded, {}", degradation.tier)?;
+            if let Some(since) = degradation.since {
+                write!(f, " since {since}")?;
+            }
+            writeln!(f)?;
+        }
+        if snapshot.fleet_budget_exhausted {
+            writeln!(f, "Fleet budget exhausted")?;
+        }
+        for plugin in &snapshot.plugins {
+            match (plugin.manual, plugin.state, plugin.next_probe) {
+                (Some(PluginOverride::Enabled), ..) => {
+                    writeln!(f, "Plugin {} enabled by hand", plugin.plugin_id)?;
+                }
+                (Some(PluginOverride::Disabled), ..) => {
+                    writeln!(f, "Plugin {} disabled by hand", plugin.plugin_id)?;
+                }
+                (None, PluginState::Probing, _) => {
+                    writeln!(f, "Plugin {} disabled for errors, probing", plugin.plugin_id)?;
+                }
+                (None, PluginState::Disabled, Some(at)) => {
+                    let id = &plugin.plugin_id;
+                    writeln!(f, "Plugin {id} disabled for errors, probed at {at}")?;
+                }
+                (None, ..) => {}
+            }
+        }
+
+        write!(
+            f,
+            "{:<24} {:<16} {:<8} {:>6}  NEXT ACTION",
+            "WALLET", "PROFILE", "STATE", "ERRORS"
+        )?;
+        for wallet in &self.wallets {
+            let state = if wallet.retirement == Some(RetirementStage::Retired) {
+                "retired"
+            } else if wallet.paused {
+                "paused"
+            } else if wallet.tripped {
+                "tripped"
+            } else if wallet.retirement.is_some() {
+                "retiring"
+            } else if wallet.afk_until.is_some() {
+                "afk"
+            } else {
+                "active"
+            };
+            write!(
+                f,
+                "\n{:<24} {:<16} {:<8} {:>6}  {}",
+                wallet.id, wallet.profile, state, wallet.consecutive_errors, wallet.next_action
+            )?;
+        }
+        Ok(())
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// HANDLE
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// A command and where to send its answer.
+pub type Envelope = (ControlCommand, oneshot::Sender<ControlResponse>);
+
+/// Sends commands to a running [`FleetService`](crate::service::FleetService).
+///
+/// Created by [`FleetService::control`](crate::service::FleetService::control).
+#[derive(Debug, Clone)]
+pub struct ControlHandle {
+    sender: mpsc::Sender<Envelope>,
+}
+
+impl ControlHandle {
+    /// Create a handle and the receiver the service reads commands from.
+    #[must_use]
+    pub fn channel() -> (Self, mpsc::Receiver<Envelope>) {
+        let (sender, receiver) = mpsc::channel(QUEUE_CAPACITY);
+        (Self { sender }, receiver)
+    }
+
+    /// Send a command and wait for the service to answer it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the service has stopped.
+    pub async fn send(&self, command: ControlCommand) -> Result<ControlResponse> {
+        let (reply, answer) = oneshot::channel();
+        self.sender
+            .send((command, reply))
+            .await
+            .map_err(|_| FleetServiceError::Control("Fleet service is not running".into()))?;
+        answer
+            .await
+            .map_err(|_| FleetServiceError::Control("Fleet service stopped".into()))
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// SERVER
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Bind the control socket to `control.listen`.
+///
+/// # Errors
+///
+/// Returns an error if the address cannot be bound.
+pub async fn bind(config: &ControlConfig) -> Result<TcpListener> {
+    TcpListener::bind(&config.listen)
+        .await
+        .map_err(|e| FleetServiceError::Control(format!("Failed to bind {}: {e}", config.listen)))
+}
+
+/// Serve the control socket on `listener` until `shutdown` becomes `true`.
+///
+/// Each connection is served until the client closes it.
+pub async fn serve(
+    listener: TcpListener,
+    config: ControlConfig,
+    handle: ControlHandle,
+    mut shutdown: watch::Receiver<bool>,
+) {
+    if let Ok(addr) = listener.local_addr() {
+        info!(%addr, "Control socket listening");
+    }
+
+    loop {
+        tokio::select! {
+            accepted = listener.accept() => match accepted {
+                Ok((stream, peer)) => {
+                    debug!(%peer, "Control connection");
+                    let config = config.clone();
+                    let handle = handle.clone();
+                    tokio::spawn(async move {
+                        if let Err(e) = serve_connection(stream, &config, &handle).await {
+                            warn!(%peer, error = %e, "Control connection failed");
+                        }
+                    });
+                }
+                Err(e) => warn!(error = %e, "Failed to accept control connection"),
+            },
+            _ = shutdown.changed() => {
+                if *shutdown.borrow() {
+                    return;
+                }
+            }
+        }
+    }
+}
+
+/// Answer the requests of one connection.
+async fn serve_connection(
+    stream: TcpStream,
+    config: &ControlConfig,
+    handle: &ControlHandle,
+) -> std::io::Result<()> {
+    let (read, mut write) = stream.into_split();
+    let mut reader = BufReader::new(read);
+    let mut line = String::new();
+
+    loop {
+        line.clear();
+        if (&mut reader)
+            .take(MAX_REQUEST_LEN)
+            .read_line(&mut line)
+            .await?
+            == 0
+        {
+            return Ok(());
+        }
+        let response = answer(line.trim(), config, handle).await;
+        let mut encoded = serde_json::to_vec(&response).map_err(std::io::Error::other)?;
+        encoded.push(b'\n');
+        write.write_all(&encoded).await?;
+    }
+}
+
+/// Check a request line and have the service answer it.
+async fn answer(line: &str, config: &ControlConfig, handle: &ControlHandle) -> ControlResponse {
+    let request: ControlRequest = match serde_json::from_str(line) {
+        Ok(request) => request,
+        Err(e) => return ControlResponse::Failed(format!("Invalid request: {e}")),
+    };
+    if config.token.is_some() && request.token != config.token {
+        warn!(command = ?request.command, "Rejected control request with a wrong token");
+        return ControlResponse::Failed("Invalid token".into());
+    }
+
+    info!(command = ?request.command, "Control command");
+    handle
+        .send(request.command)
+        .await
+        .unwrap_or_else(|e| ControlResponse::Failed(e.to_string()))
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// CLIENT
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Send one command to the control socket of a running fleet.
+///
+/// # Errors
+///
+/// Returns an error if the socket cannot be reached or answers with
+/// something other than a [`ControlResponse`].
+pub async fn request(config: &ControlConfig, command: ControlCommand) -> Result<ControlResponse> {
+    let io_error =
+        |e: std::io::Error| FleetServiceError::Control(format!("{}: {e}", config.listen));
+
+    let stream = TcpStream::connect(&config.listen).await.map_err(io_error)?;
+    let (read, mut write) = stream.into_split();
+
+    let request = ControlRequest {
+        token: config.token.clone(),
+        command,
+    };
+    let mut encoded =
+        serde_json::to_vec(&request).map_err(|e| FleetServiceError::Internal(e.to_string()))?;
+    encoded.push(b'\n');
+    write.write_all(&encoded).await.map_err(io_error)?;
+
+    let mut line = String::new();
+    BufReader::new(read)
+        .read_line(&mut line)
+        .await
+        .map_err(io_error)?;
+    serde_json::from_str(&line)
+        .map_err(|e| FleetServiceError::Control(format!("Invalid response: {e}")))
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    use alloy::primitives::{Address, TxHash, U256};
+    use async_trait::async_trait;
+    use chrono::TimeZone;
+    use evm_provider::mock::MockProvider;
+    use evm_provider::pool::ProviderPool;
+    use fleet_core::clock::{Clock, VirtualClock};
+    use fleet_core::plugins::{
+        Action, ActionId, ActionPlugin, ActionStatus, PluginContext, PluginRegistry,
+    };
+    use fleet_core::profiles::BehaviorProfile;
+    use fleet_core::safety::Spend;
+    use fleet_core::wallet::{RetirementStage, WalletState};
+    use fleet_core::{ErrorClass, FleetError};
+
+    use super::*;
+    use crate::config::Settings;
+    use crate::service::{FleetService, Runtime};
+    use crate::signer::Keyring;
+
+    /// Two wallets that act hourly while awake, never going AFK; `w1` may
+    /// spend on three actions a day.
+    const CONFIG: &str = r#"
+        [service]
+        tick_interval_ms = 10
+
+        [chain]
+        chain_id = 31337
+        rpc_url = "http://localhost:8545"
+        chain_type = "mock"
+
+        [plugins]
+        enabled = ["counting"]
+
+        [[wallets]]
+        id = "w1"
+        address = "0x0000000000000000000000000000000000000001"
+        profile = "steady"
+        budget = { daily_actions = 3 }
+
+        [[wallets]]
+        id = "w2"
+        address = "0x0000000000000000000000000000000000000002"
+        profile = "steady"
+
+        [profiles.steady]
+        afk_probability = 0.0
+    "#;
+
+    /// Plugin that always decides `counting.act`, and records what it runs.
+    #[derive(Debug, Default)]
+    struct CountingPlugin {
+        executed: Mutex<Vec<(String, ActionId)>>,
+        failing: AtomicBool,
+    }
+
+    impl CountingPlugin {
+        fn executions(&self, wallet_id: &str) -> usize {
+            let executed = self.executed.lock().unwrap();
+            executed.iter().filter(|(id, _)| id == wallet_id).count()
+        }
+
+        fn fail(&self, failing: bool) {
+            self.failing.store(failing, Ordering::SeqCst);
+        }
+    }
+
+    #[async_trait]
+    impl ActionPlugin for CountingPlugin {
+        fn id(&self) -> &'static str {
+            "counting"
+        }
+
+        fn name(&self) -> &'static str {
+            "Counting"
+        }
+
+        fn available_actions(&self) -> Vec<ActionId> {
+            vec![
+                ActionId::new("counting.act"),
+                ActionId::new("counting.other"),
+            ]
+        }
+
+        async fn decide_action(
+            &self,
+            _wallet: &WalletState,
+            _profile: &BehaviorProfile,
+            _context: &mut PluginContext<'_>,
+        ) -> fleet_core::Result<Option<Action>> {
+            Ok(Some(Action::new("counting.act", "Act")))
+        }
+
+        async fn execute_action(
+            &self,
+            action: &Action,
+            wallet: &WalletState,
+            _nonce: u64,
+        ) -> fleet_core::Result<ActionResult> {
+            if self.failing.load(Ordering::SeqCst) {
+                return Err(FleetError::plugin(ErrorClass::Permanent, "broken"));
+            }
+            self.executed
+                .lock()
+                .unwrap()
+                .push((wallet.id.clone(), action.id.clone()));
+            Ok(ActionResult::success(TxHash::ZERO))
+        }
+
+        async fn read_state(&self, _address: Address) -> fleet_core::Result<serde_json::Value> {
+            Ok(serde_json::Value::Null)
+        }
+    }
+
+    /// A clock stopped at noon, inside the profile's active hours.
+    fn noon() -> Arc<VirtualClock> {
+        let start = Utc.with_ymd_and_hms(2025, 1, 1, 12, 0, 0).unwrap();
+        Arc::new(VirtualClock::new(start))
+    }
+
+    fn service(
+        settings: Settings,
+        clock: &Arc<VirtualClock>,
+    ) -> (FleetService, Arc<CountingPlugin>) {
+        service_on(settings, clock, Arc::new(MockProvider::with_chain_id(31337)))
+    }
+
+    fn service_on(
+        settings: Settings,
+        clock: &Arc<VirtualClock>,
+        chain: Arc<MockProvider>,
+    ) -> (FleetService, Arc<CountingPlugin>) {
+        let plugin = Arc::new(CountingPlugin::default());
+        let mut registry = PluginRegistry::new();
+        registry.register(Arc::clone(&plugin) as Arc<dyn ActionPlugin>).unwrap();
+        let signers = Keyring::development(settings.wallets.iter().map(|w| w.id.as_str())).unwrap();
+
+        let runtime = Runtime {
+            pool: Arc::new(ProviderPool::single("primary", chain)),
+            registry,
+            clock: Arc::clone(clock) as _,
+            seed: Some(7),
+            signers,
+        };
+        (FleetService::with_runtime(settings, false, runtime), plugin)
+    }
+
+    fn trigger(wallet_id: &str, action_id: &str) -> ControlCommand {
+        ControlCommand::Trigger {
+            wallet_id: wallet_id.into(),
+            action_id: action_id.into(),
+        }
+    }
+
+    fn failure(response: ControlResponse) -> String {
+        match response {
+            ControlResponse::Failed(error) => error,
+            other => format!("unexpected success: {other:?}"),
+        }
+    }
+
+    /// Wait up to 5 seconds for `condition` to hold.
+    async fn eventually(condition: impl Fn() -> bool) {
+        for _ in 0..500 {
+            if condition() {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert!(condition(), "condition not met within 5s");
+    }
+
+    #[tokio::test]
+    async fn running_service_answers_over_the_socket() {
+        let clock = noon();
+        let (mut service, plugin) = service(toml::from_str(CONFIG).unwrap(), &clock);
+        let config = ControlConfig {
+            enabled: true,
+            listen: "127.0.0.1:0".into(),
+            token: Some("secret".into()),
+        };
+        let listener = bind(&config).await.unwrap();
+        let client = ControlConfig {
+            listen: listener.local_addr().unwrap().to_string(),
+            ..config.clone()
+        };
+
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        tokio::spawn(serve(
+            listener,
+            config,
+            service.control(),
+            shutdown_rx.clone(),
+        ));
+        let running = tokio::spawn(service.run(shutdown_rx));
+
+        // Both wallets are due at start
+        eventually(|| plugin.executions("w1") == 1 && plugin.executions("w2") == 1).await;
+
+        // A paused wallet is never scheduled, even once it is due again
+        let paused = request(
+            &client,
+            ControlCommand::Pause {
+                wallet_id: "w1".into(),
+            },
+        )
+        .await;
+        assert!(matches!(paused.unwrap(), ControlResponse::Done(_)));
+        clock.advance(chrono::Duration::days(1));
+        eventually(|| plugin.executions("w2") == 2).await;
+        assert_eq!(plugin.executions("w1"), 1);
+
+        let ControlResponse::Status(status) =
+            request(&client, ControlCommand::Status).await.unwrap()
+        else {
+            unreachable!("status answers with the status");
+        };
+        let w1 = &status.wallets[0];
+        assert_eq!((w1.id.as_str(), w1.paused), ("w1", true));
+        assert!(w1.next_action <= clock.now());
+        assert!(!status.wallets[1].paused);
+        assert_eq!(status.snapshot.active_wallets, 1);
+        assert_eq!(status.snapshot.total_actions, 3);
+        assert!(status.to_string().contains("paused"));
+
+        // Nor can it be triggered
+        let rejected = request(&client, trigger("w1", "counting.act"))
+            .await
+            .unwrap();
+        assert!(failure(rejected).contains("paused"));
+
+        // Resumed, the overdue wallet acts on the next tick
+        let resumed = request(
+            &client,
+            ControlCommand::Resume {
+                wallet_id: "w1".into(),
+            },
+        )
+        .await;
+        assert!(matches!(resumed.unwrap(), ControlResponse::Done(_)));
+        eventually(|| plugin.executions("w1") == 2).await;
+
+        // Requests without the token are turned away
+        for token in [None, Some("guess".to_string())] {
+            let intruder = ControlConfig {
+                token,
+                ..client.clone()
+            };
+            let response = request(&intruder, ControlCommand::Status).await.unwrap();
+            assert_eq!(failure(response), "Invalid token");
+        }
+
+        shutdown_tx.send(true).unwrap();
+        running.await.unwrap().unwrap();
+        assert!(request(&client, ControlCommand::Status).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn trigger_respects_breakers_and_budgets() {
+        let (mut service, plugin) = service(toml::from_str(CONFIG).unwrap(), &noon());
+
+        // The plugin decides another action, so the forced one runs without data
+        let response = service
+            .handle_command(trigger("w1", "counting.other"))
+            .await;
+        let ControlResponse::Triggered(result) = response else {
+            unreachable!("{response:?}");
+        };
+        assert_eq!(result.status, ActionStatus::Succeeded);
+        assert_eq!(
+            plugin.executed.lock().unwrap().as_slice(),
+            [("w1".to_string(), ActionId::new("counting.other"))]
+        );
+
+        let unknown = service.handle_command(trigger("w1", "nope.act")).await;
+        assert!(failure(unknown).contains("No enabled plugin offers action nope.act"));
+        let unknown = service.handle_command(trigger("w9", "counting.act")).await;
+        assert!(failure(unknown).contains("Wallet not found: w9"));
+
+        // Failures trip the wallet's breaker, and a tripped wallet is not triggered
+        plugin.fail(true);
+        for _ in 0..5 {
+            let response = service.handle_command(trigger("w1", "counting.act")).await;
+            assert!(matches!(response, ControlResponse::Triggered(r) if r.status.is_failure()));
+        }
+        plugin.fail(false);
+        let tripped = service.handle_command(trigger("w1", "counting.act")).await;
+        assert!(failure(tripped).contains("Circuit breaker of w1 is tripped"));
+
+        let reset = ControlCommand::ResetBreaker {
+            wallet_id: Some("w1".into()),
+        };
+        assert!(matches!(
+            service.handle_command(reset).await,
+            ControlResponse::Done(_)
+        ));
+        assert_eq!(service.status().wallets[0].consecutive_errors, 0);
+
+        // Two more successes use up w1's three actions a day
+        for _ in 0..2 {
+            let response = service.handle_command(trigger("w1", "counting.act")).await;
+            assert!(matches!(response, ControlResponse::Triggered(_)));
+        }
+        let over_budget = service.handle_command(trigger("w1", "counting.act")).await;
+        assert!(failure(over_budget).contains("Budget of w1 is exhausted"));
+        assert_eq!(plugin.executions("w1"), 3);
+
+        // w2 has no cap of its own
+        let response = service.handle_command(trigger("w2", "counting.act")).await;
+        assert!(matches!(response, ControlResponse::Triggered(_)));
+    }
+
+    #[tokio::test]
+    async fn disabled_plugins_skip_wallets_without_errors() {
+        let clock = noon();
+        let (mut service, plugin) = service(toml::from_str(CONFIG).unwrap(), &clock);
+        let switch = |switch| ControlCommand::Plugin {
+            plugin_id: "counting".into(),
+            switch,
+        };
+
+        let response = service.handle_command(switch(PluginSwitch::Disable)).await;
+        assert!(matches!(response, ControlResponse::Done(_)));
+        service.process_tick().await;
+        assert_eq!(plugin.executions("w1") + plugin.executions("w2"), 0);
+
+        // Skipped cycles are not errors, and the plugin shows as disabled
+        let status = service.status();
+        assert!(status.wallets.iter().all(|w| w.consecutive_errors == 0));
+        assert_eq!(status.snapshot.plugins[0].state, PluginState::Disabled);
+        assert!(status.to_string().contains("Plugin counting disabled by hand"));
+
+        // Back to automatic, the wallets act on their next turn
+        let response = service.handle_command(switch(PluginSwitch::Auto)).await;
+        assert!(matches!(response, ControlResponse::Done(_)));
+        clock.advance(chrono::Duration::days(1));
+        service.process_tick().await;
+        assert_eq!((plugin.executions("w1"), plugin.executions("w2")), (1, 1));
+        assert_eq!(service.snapshot().plugins[0].state, PluginState::Enabled);
+
+        let unknown = ControlCommand::Plugin {
+            plugin_id: "nope".into(),
+            switch: PluginSwitch::Enable,
+        };
+        let unknown = service.handle_command(unknown).await;
+        assert!(failure(unknown).contains("Plugin nope is not enabled"));
+    }
+
+    fn report(response: ControlResponse) -> DailyReport {
+        match response {
+            ControlResponse::Report(report) => *report,
+            other => unreachable!("{other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn report_covers_the_day_so_far() {
+        let clock = noon();
+        let (mut service, plugin) = service(toml::from_str(CONFIG).unwrap(), &clock);
+        service.handle_command(trigger("w1", "counting.act")).await;
+        plugin.fail(true);
+        for _ in 0..5 {
+            service.handle_command(trigger("w2", "counting.act")).await;
+        }
+        clock.advance(chrono::Duration::hours(1));
+
+        let all = report(service.handle_command(ControlCommand::Report { wallet_id: None }).await);
+        assert_eq!((all.from, all.to), (noon().now(), clock.now()));
+        let activity: Vec<_> = all
+            .wallets
+            .iter()
+            .map(|w| (w.wallet_id.as_str(), w.activity.successes, w.activity.failures))
+            .collect();
+        assert_eq!(activity, [("w1", 1, 0), ("w2", 0, 5)]);
+        assert_eq!(all.wallets[1].activity.breaker_trips, 1);
+        assert_eq!(all.totals.activity.actions, 6);
+
+        let one = ControlCommand::Report {
+            wallet_id: Some("w2".into()),
+        };
+        let w2 = report(service.handle_command(one).await);
+        assert_eq!(w2.wallets.len(), 1);
+        assert_eq!(w2.totals, all.totals);
+        let unknown = ControlCommand::Report {
+            wallet_id: Some("w9".into()),
+        };
+        assert!(failure(service.handle_command(unknown).await).contains("Wallet not found: w9"));
+    }
+
+    #[tokio::test]
+    async fn reload_profiles_from_config_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("fleet.toml");
+        std::fs::write(&path, CONFIG).unwrap();
+        let (service, plugin) = service(Settings::load(&path).unwrap(), &noon());
+        let mut service = service.with_config_path(&path);
+
+        for _ in 0..2 {
+            let response = service.handle_command(trigger("w2", "counting.act")).await;
+            assert!(matches!(response, ControlResponse::Triggered(_)));
+        }
+
+        // Profiles the wallets cannot use are rejected, the old ones stay
+        std::fs::write(
+            &path,
+            CONFIG.replace("[profiles.steady]", "[profiles.other]"),
+        )
+        .unwrap();
+        let invalid = service.handle_command(ControlCommand::ReloadProfiles).await;
+        assert!(failure(invalid).contains("Reloaded profiles are invalid"));
+        let response = service.handle_command(trigger("w2", "counting.act")).await;
+        assert!(matches!(response, ControlResponse::Triggered(_)));
+
+        // A profile budget caps w2 from now on
+        let capped = CONFIG.replace(
+            "afk_probability = 0.0",
+            "afk_probability = 0.0\nbudget = { daily_actions = 3 }",
+        );
+        std::fs::write(&path, capped).unwrap();
+        let reloaded = service.handle_command(ControlCommand::ReloadProfiles).await;
+        assert!(
+            matches!(reloaded, ControlResponse::Done(message) if message == "Reloaded 1 profiles")
+        );
+        let over_budget = service.handle_command(trigger("w2", "counting.act")).await;
+        assert!(failure(over_budget).contains("Budget of w2 is exhausted"));
+        assert_eq!(plugin.executions("w2"), 3);
+    }
+
+    /// Settings of [`CONFIG`] with a `[review]` section.
+    fn reviewed(review: &str) -> Settings {
+        toml::from_str(&format!("{CONFIG}\n[review]\n{review}")).unwrap()
+    }
+
+    fn plans(response: ControlResponse) -> Vec<PlannedBatch> {
+        match response {
+            ControlResponse::Plans(batches) => batches,
+            other => unreachable!("{other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn planned_batches_run_after_the_review_delay() {
+        let clock = noon();
+        let settings = reviewed("mode = \"delay\"\ndelay_secs = 60");
+        let (mut service, plugin) = service(settings, &clock);
+
+        // Both wallets' actions are planned into one batch, and wait
+        service.process_tick().await;
+        let batches = plans(service.handle_command(ControlCommand::Plans).await);
+        assert_eq!(batches.len(), 1);
+        let wallets: Vec<_> = batches[0].actions.iter().map(|p| p.wallet_id.as_str()).collect();
+        assert_eq!(wallets, ["w1", "w2"]);
+        assert_eq!(batches[0].runs_at, Some(clock.now() + chrono::Duration::seconds(60)));
+        assert_eq!(batches[0].actions[0].estimate, Spend::action());
+
+        // Wallets under review are not planned again
+        clock.advance(chrono::Duration::seconds(30));
+        service.process_tick().await;
+        assert_eq!(plans(service.handle_command(ControlCommand::Plans).await).len(), 1);
+        assert_eq!(plugin.executions("w1") + plugin.executions("w2"), 0);
+
+        // Unrejected, the batch runs once the delay is over
+        clock.advance(chrono::Duration::seconds(30));
+        service.process_tick().await;
+        assert_eq!((plugin.executions("w1"), plugin.executions("w2")), (1, 1));
+        assert!(plans(service.handle_command(ControlCommand::Plans).await).is_empty());
+        assert_eq!(service.snapshot().successful_actions, 2);
+    }
+
+    #[tokio::test]
+    async fn rejected_actions_are_not_run_or_counted() {
+        let clock = noon();
+        let settings = reviewed("mode = \"delay\"\ndelay_secs = 60");
+        let (mut service, plugin) = service(settings, &clock);
+        service.process_tick().await;
+
+        let reject = |wallet_id: &str| ControlCommand::Reject {
+            batch_id: 1,
+            wallet_id: Some(wallet_id.into()),
+        };
+        let rejected = service.handle_command(reject("w1")).await;
+        assert!(
+            matches!(rejected, ControlResponse::Done(message) if message.contains("Rejected 1"))
+        );
+        let again = service.handle_command(reject("w1")).await;
+        assert!(failure(again).contains("w1 has no action to reject in batch 1"));
+
+        let batches = plans(service.handle_command(ControlCommand::Plans).await);
+        assert!(batches[0].actions[0].rejected);
+        assert!(batches[0].to_string().contains("(rejected)"));
+
+        // Only w2 acts; w1's rejected action is neither a success nor a failure
+        clock.advance(chrono::Duration::seconds(60));
+        service.process_tick().await;
+        assert_eq!((plugin.executions("w1"), plugin.executions("w2")), (0, 1));
+        let snapshot = service.snapshot();
+        assert_eq!((snapshot.total_actions, snapshot.failed_actions), (1, 0));
+        let status = service.status();
+        assert_eq!(status.wallets[0].consecutive_errors, 0);
+        assert!(!status.wallets[0].tripped);
+
+        // A batch with all its actions rejected is dropped
+        clock.advance(chrono::Duration::days(1));
+        service.process_tick().await;
+        let whole = ControlCommand::Reject {
+            batch_id: 2,
+            wallet_id: None,
+        };
+        assert!(matches!(service.handle_command(whole).await, ControlResponse::Done(_)));
+        assert!(plans(service.handle_command(ControlCommand::Plans).await).is_empty());
+    }
+
+    #[tokio::test]
+    async fn strict_review_waits_for_approval() {
+        let dir = tempfile::tempdir().unwrap();
+        let journal = dir.path().join("plans.jsonl");
+        let clock = noon();
+        let settings = reviewed(&format!("mode = \"strict\"\njournal = {journal:?}"));
+        let (mut service, plugin) = service(settings, &clock);
+
+        // However long it waits, an unapproved batch does not run
+        service.process_tick().await;
+        clock.advance(chrono::Duration::days(1));
+        service.process_tick().await;
+        let batches = plans(service.handle_command(ControlCommand::Plans).await);
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].runs_at, None);
+        assert!(batches[0].to_string().contains("awaiting approval"));
+        assert_eq!(plugin.executions("w1") + plugin.executions("w2"), 0);
+
+        let unknown = service.handle_command(ControlCommand::Approve { batch_id: 9 }).await;
+        assert!(failure(unknown).contains("No planned batch 9"));
+        let approved = service.handle_command(ControlCommand::Approve { batch_id: 1 }).await;
+        assert!(matches!(approved, ControlResponse::Done(_)));
+
+        // The approved batch runs on the next tick, which plans the next batch
+        service.process_tick().await;
+        assert_eq!((plugin.executions("w1"), plugin.executions("w2")), (1, 1));
+        let batches = plans(service.handle_command(ControlCommand::Plans).await);
+        assert_eq!(batches.iter().map(|b| b.id).collect::<Vec<_>>(), [2]);
+
+        let events: Vec<String> = std::fs::read_to_string(&journal)
+            .unwrap()
+            .lines()
+            .map(|line| {
+                let entry: serde_json::Value = serde_json::from_str(line).unwrap();
+                entry["event"].to_string()
+            })
+            .collect();
+        assert_eq!(
+            events,
+            [r#""planned""#, r#""approved""#, r#""executed""#, r#""executed""#, r#""planned""#]
+        );
+    }
+
+    #[test]
+    fn requests_are_flat_json() {
+        let request = ControlRequest {
+            token: Some("secret".into()),
+            command: ControlCommand::ResetBreaker { wallet_id: None },
+        };
+        let encoded = serde_json::to_string(&request).unwrap();
+        assert_eq!(
+            encoded,
+            r#"{"token":"secret","command":"reset_breaker","wallet_id":null}"#
+        );
+
+        let decoded: ControlRequest = serde_json::from_str(
+            r#"{"command":"plugin","plugin_id":"ghostnet","switch":"disable"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            decoded.command,
+            ControlCommand::Plugin {
+                plugin_id: "ghostnet".into(),
+                switch: PluginSwitch::Disable,
+            }
+        );
+
+        let decoded: ControlRequest =
+            serde_json::from_str(r#"{"command":"pause","wallet_id":"whale_1"}"#).unwrap();
+        assert_eq!(decoded.token, None);
+        assert_eq!(
+            decoded.command,
+            ControlCommand::Pause {
+                wallet_id: "whale_1".into()
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn retired_wallets_are_replaced_by_a_standby() {
+        let clock = noon();
+        let config = format!(
+            r#"{CONFIG}
+            [[wallets]]
+            id = "w3"
+            address = "0x0000000000000000000000000000000000000003"
+            profile = "fresh"
+            standby = true
+
+            [profiles.fresh]
+            afk_probability = 0.0
+            "#
+        );
+        let chain = Arc::new(MockProvider::with_chain_id(31337));
+        let w1 = Address::with_last_byte(1);
+        chain.set_balance(w1, U256::from(10_u64.pow(18)));
+        let settings = toml::from_str(&config).unwrap();
+        let (mut service, plugin) = service_on(settings, &clock, Arc::clone(&chain));
+        let status = |service: &FleetService| {
+            let status = service.status();
+            let wallets = status.wallets.iter();
+            wallets.map(|w| (w.id.clone(), w.profile.clone(), w.retirement)).collect::<Vec<_>>()
+        };
+        assert_eq!(status(&service).len(), 2);
+
+        let retire = |sweep_to: Address| ControlCommand::Retire {
+            wallet_id: "w1".into(),
+            sweep_to: Some(sweep_to),
+        };
+        assert!(failure(service.handle_command(retire(w1)).await).contains("itself"));
+        let retiring = service.handle_command(retire(Address::repeat_byte(0xaa))).await;
+        assert!(matches!(retiring, ControlResponse::Done(_)));
+
+        // With nothing open, w1 sweeps its balance instead of acting
+        service.process_tick().await;
+        assert_eq!((plugin.executions("w1"), plugin.executions("w2")), (0, 1));
+        assert_eq!(service.snapshot().successful_actions, 2);
+        let retiring = ("w1".into(), "steady".into(), Some(RetirementStage::Retiring));
+        assert_eq!(status(&service)[0], retiring);
+        assert!(service.status().to_string().contains("retiring"));
+
+        // With only dust left it is retired, and the standby takes its profile
+        chain.set_balance(w1, U256::from(1_000));
+        clock.advance(chrono::Duration::days(1));
+        service.process_tick().await;
+        assert_eq!(
+            status(&service),
+            [
+                ("w1".into(), "steady".into(), Some(RetirementStage::Retired)),
+                ("w2".into(), "steady".into(), None),
+                ("w3".into(), "steady".into(), None),
+            ]
+        );
+        let w3 = &service.wallets()["w3"];
+        assert!(w3.last_executed.is_empty() && w3.is_active_at(clock.now()));
+
+        // The retired wallet is never scheduled again, nor resumed
+        clock.advance(chrono::Duration::days(1));
+        service.process_tick().await;
+        assert_eq!(plugin.executions("w1"), 0);
+        assert_eq!(plugin.executions("w3"), 1);
+        let resume = ControlCommand::Resume {
+            wallet_id: "w1".into(),
+        };
+        assert!(failure(service.handle_command(resume).await).contains("retired"));
+        assert!(failure(service.handle_command(trigger("w1", "counting.act")).await)
+            .contains("retired"));
+        assert_eq!(service.snapshot().active_wallets, 2);
+    }
+}
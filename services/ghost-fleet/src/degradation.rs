@@ -0,0 +1,431 @@
+//! Degradation of the fleet while its RPC endpoints are partly available.
+//!
+//! An endpoint can fail halfway: answering reads while the sequencer behind
+//! it drops every transaction, say. Wallets would then keep deciding actions
+//! that fail, and trip their circuit breakers over errors that are not
+//! theirs. With `degradation.enabled` set, the [`DegradationMonitor`] probes
+//! every endpoint of the pool each `degradation.probe_interval_secs` instead,
+//! and the service moves through the [tiers](DegradationTier) of a
+//! [`Degradation`]:
+//!
+//! | Tier | The service |
+//! |------|-------------|
+//! | 0, normal | runs as usual |
+//! | 1, read-only | refreshes the due wallets and consults their plugins, but journals and logs each decided action as withheld instead of executing it |
+//! | 2, paused | does nothing but probe |
+//!
+//! While degraded, circuit breakers are [frozen](CircuitBreaker::freeze)
+//! and the [`GlobalBreaker`] stands aside, so an outage neither trips wallets
+//! nor opens it on top. Each tier change is logged, journaled (see
+//! [`crate::review`]), shown by `ctl status` and in the fleet's
+//! [`FleetSnapshot`], and POSTed as JSON to `degradation.alert_webhook_url`
+//! if set:
+//!
+//! ```text
+//! {"fleet":"alpha","from":"normal","to":"read_only","at":"2025-01-01T12:00:00Z",
+//!  "capabilities":{"reads":true,"sends":false}}
+//! ```
+//!
+//! # Probes
+//!
+//! A probe reads the block number, and sends a signed transaction with too
+//! little gas to run, the standard way and, where the endpoint offers it,
+//! through the realtime API. Sends only count as working if a node
+//! [refuses](ProviderError::is_refusal) that transaction for its gas: an
+//! endpoint that takes it, or fails it any other way, is not passing sends
+//! on to a node that checks them. Throttled endpoints are degraded, not
+//! down: a rate-limited read counts as answered, a rate-limited send as
+//! failed. Probes go to the providers behind the pool's endpoints, and do
+//! not count toward the endpoints' stats or failures. The fleet can do
+//! whatever any of its endpoints can, as wallets fail over between them.
+//!
+//! A probe cannot tell whether the transactions a node takes are mined, so
+//! the monitor also counts what became of the actions executed since the
+//! last probe: if more of their transactions were lost than landed, the
+//! probe finds sends failing whatever the endpoints answered. While the
+//! realtime API fails and standard sends work, the fleet stays in tier 0
+//! and submits the standard way.
+//!
+//! [`CircuitBreaker::freeze`]: fleet_core::safety::CircuitBreaker::freeze
+//! [`FleetSnapshot`]: fleet_core::metrics::FleetSnapshot
+//! [`GlobalBreaker`]: fleet_core::safety::GlobalBreaker
+
+use std::time::Duration;
+
+use alloy::primitives::{Address, B256, Bytes};
+use alloy::signers::local::PrivateKeySigner;
+use chrono::{DateTime, Utc};
+use evm_provider::pool::ProviderPool;
+use evm_provider::{
+    ExtendedChainProvider, LocalSigner, ProviderError, TransactionRequest, TransactionSigner,
+};
+use fleet_core::plugins::{ActionResult, ActionStatus};
+use fleet_core::safety::{
+    Capabilities, Degradation, DegradationStatus, DegradationTier, TierChange,
+};
+use serde::Serialize;
+use tracing::{info, warn};
+
+use crate::config::DegradationConfig;
+
+/// Capabilities of a pool without endpoints.
+const NOTHING: Capabilities = Capabilities {
+    reads: false,
+    sends: false,
+    realtime: None,
+};
+
+/// Key the probe transaction is signed with. The transaction is refused
+/// for its gas, whatever the account holds.
+const PROBE_KEY: B256 = B256::repeat_byte(0x01);
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// PROBES
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// What `provider` can do.
+pub async fn probe<P: ExtendedChainProvider>(provider: &P) -> Capabilities {
+    let answered = |read: evm_provider::Result<u64>| match read {
+        Ok(_) => true,
+        Err(e) => e.is_rate_limited(),
+    };
+    let reads = answered(provider.get_block_number().await);
+    let Ok(tx) = probe_transaction(provider.chain_id()).await else {
+        warn!("Failed to sign the probe transaction");
+        return Capabilities { reads, ..NOTHING };
+    };
+    let realtime = if provider.supports_realtime() {
+        Some(refused(provider.send_realtime(tx.clone()).await))
+    } else {
+        None
+    };
+    let standard = refused(provider.send_raw_transaction(tx).await);
+    Capabilities {
+        reads,
+        sends: standard || realtime == Some(true),
+        realtime,
+    }
+}
+
+/// What the endpoints of `pool` can do together.
+pub async fn probe_pool<P: ExtendedChainProvider>(pool: &ProviderPool<P>) -> Capabilities {
+    let probes = pool
+        .endpoints()
+        .iter()
+        .map(|endpoint| probe(endpoint.inner()));
+    futures::future::join_all(probes)
+        .await
+        .into_iter()
+        .reduce(Capabilities::or)
+        .unwrap_or(NOTHING)
+}
+
+/// A transaction on `chain_id` with too little gas to run, which any node
+/// that checks what it is sent refuses.
+async fn probe_transaction(chain_id: u64) -> evm_provider::Result<Bytes> {
+    let signer = PrivateKeySigner::from_bytes(&PROBE_KEY)
+        .map_err(|e| ProviderError::Signing(e.to_string()))?;
+    let request = TransactionRequest::new()
+        .to(Address::ZERO)
+        .nonce(0)
+        .gas_limit(1)
+        .gas_price(1);
+    LocalSigner::new(signer)
+        .sign_transaction(&request, chain_id)
+        .await
+}
+
+/// Whether the answer to the probe transaction came from a node refusing
+/// it, so that sends reach a node.
+fn refused<T>(answer: evm_provider::Result<T>) -> bool {
+    answer.is_err_and(|e| e.is_refusal())
+}
+
+/// Transactions of the actions executed since the last probe, by what
+/// became of them.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct SendOutcomes {
+    /// Mined, whether or not they reverted.
+    landed: u32,
+
+    /// Sent but never mined, or failed to send for a reason that may pass.
+    lost: u32,
+}
+
+impl SendOutcomes {
+    /// Count the transaction of an action that ended with `result`.
+    fn record(&mut self, result: &ActionResult) {
+        let retryable = result.error.as_ref().is_some_and(|e| e.retryable);
+        match result.status {
+            ActionStatus::Succeeded | ActionStatus::Reverted => self.landed += 1,
+            ActionStatus::Dropped if result.tx_hash.is_some() || retryable => self.lost += 1,
+            _ => {}
+        }
+    }
+
+    /// Whether sends fail, as more transactions were lost than landed.
+    const fn failing(self) -> bool {
+        self.lost > self.landed
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// MONITOR
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Tier change as POSTed to the alert webhook.
+#[derive(Serialize)]
+struct Alert<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fleet: Option<&'a str>,
+    #[serde(flatten)]
+    change: &'a TierChange,
+}
+
+/// Probes the pool on schedule and keeps the fleet's tier, see the
+/// [module docs](self).
+#[derive(Debug)]
+pub struct DegradationMonitor {
+    degradation: Degradation,
+
+    /// Time between two probes.
+    interval: chrono::Duration,
+
+    /// When the next probe is due.
+    next_probe: DateTime<Utc>,
+
+    /// URL tier changes are POSTed to, and the client posting them.
+    webhook: Option<(String, reqwest::Client)>,
+
+    /// What became of the transactions sent since the last probe.
+    sends: SendOutcomes,
+}
+
+impl DegradationMonitor {
+    /// Monitor probing as `config` says, first at `now`.
+    #[must_use]
+    pub fn new(config: &DegradationConfig, now: DateTime<Utc>) -> Self {
+        let webhook = config.alert_webhook_url.clone().map(|url| {
+            let client = reqwest::Client::builder()
+                .timeout(Duration::from_secs(config.alert_webhook_timeout_secs))
+                .build()
+                .unwrap_or_default();
+            (url, client)
+        });
+        Self {
+            degradation: Degradation::new(config.to_settings()),
+            interval: i64::try_from(config.probe_interval_secs)
+                .map_or(chrono::Duration::MAX, chrono::Duration::seconds),
+            next_probe: now,
+            webhook,
+            sends: SendOutcomes::default(),
+        }
+    }
+
+    /// Tier the fleet is in.
+    #[must_use]
+    pub const fn tier(&self) -> DegradationTier {
+        self.degradation.tier()
+    }
+
+    /// Tier of the fleet and what the probes found.
+    #[must_use]
+    pub const fn status(&self) -> DegradationStatus {
+        self.degradation.status()
+    }
+
+    /// When the next probe is due.
+    #[must_use]
+    pub const fn next_probe(&self) -> DateTime<Utc> {
+        self.next_probe
+    }
+
+    /// Whether the last probe found the realtime API failing where the
+    /// endpoints offer it.
+    #[must_use]
+    pub fn realtime_down(&self) -> bool {
+        self.degradation
+            .status()
+            .capabilities
+            .is_some_and(|c| c.realtime == Some(false))
+    }
+
+    /// Count what became of the transaction of an executed action toward
+    /// the next probe.
+    pub fn record_result(&mut self, result: &ActionResult) {
+        self.sends.record(result);
+    }
+
+    /// Probe `pool` if a probe is due at `now`, returning the tier change
+    /// it completed, if any.
+    ///
+    /// Sends fail whatever the endpoints answered if more of the
+    /// transactions sent since the last probe were lost than landed.
+    pub async fn probe_if_due<P: ExtendedChainProvider>(
+        &mut self,
+        pool: &ProviderPool<P>,
+        now: DateTime<Utc>,
+    ) -> Option<TierChange> {
+        if now < self.next_probe {
+            return None;
+        }
+        let mut capabilities = probe_pool(pool).await;
+        if std::mem::take(&mut self.sends).failing() {
+            capabilities.sends = false;
+        }
+        self.next_probe = now + self.interval;
+        self.degradation.record(capabilities, now)
+    }
+
+    /// POST `change` of fleet `fleet_id` to the alert webhook, if set, on a
+    /// task of its own.
+    pub fn alert(&self, fleet_id: Option<&str>, change: &TierChange) {
+        let Some((url, client)) = &self.webhook else {
+            return;
+        };
+        let alert = Alert {
+            fleet: fleet_id,
+            change,
+        };
+        let body = match serde_json::to_string(&alert) {
+            Ok(body) => body,
+            Err(e) => {
+                warn!(error = %e, "Failed to encode degradation alert");
+                return;
+            }
+        };
+        let (url, client) = (url.clone(), client.clone());
+        tokio::spawn(async move {
+            let response = client
+                .post(&url)
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .body(body)
+                .send()
+                .await
+                .and_then(reqwest::Response::error_for_status);
+            match response {
+                Ok(_) => info!("Posted degradation alert"),
+                Err(e) => warn!(error = %e, "Failed to post degradation alert"),
+            }
+        });
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::sync::Arc;
+
+    use alloy::primitives::TxHash;
+    use evm_provider::AssignmentStrategy;
+    use evm_provider::fault::{Fault, FaultPlan, Method};
+    use evm_provider::mock::MockProvider;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn probes_tell_reads_from_sends() {
+        let provider = MockProvider::new();
+        assert_eq!(probe(&provider).await.tier(), DegradationTier::Normal);
+
+        provider.set_fault_plan(Arc::new(
+            FaultPlan::new(0)
+                .fail_next(Method::SendRawTransaction, 1, Fault::Connection)
+                .fail_next(Method::BlockNumber, 1, Fault::Timeout)
+                .fail_next(Method::BlockNumber, 1, Fault::Connection),
+        ));
+        assert_eq!(probe(&provider).await, NOTHING);
+        let reads_failing = Capabilities {
+            reads: false,
+            sends: true,
+            realtime: None,
+        };
+        assert_eq!(probe(&provider).await, reads_failing);
+
+        // Throttling degrades, but does not pause
+        provider.set_fault_plan(Arc::new(
+            FaultPlan::new(0)
+                .fail_next(Method::BlockNumber, 1, Fault::RateLimited)
+                .fail_next(Method::SendRawTransaction, 1, Fault::RateLimited),
+        ));
+        assert_eq!(probe(&provider).await.tier(), DegradationTier::ReadOnly);
+        assert_eq!(probe(&provider).await.tier(), DegradationTier::Normal);
+    }
+
+    #[test]
+    fn only_refused_probes_count_as_sends() {
+        let refusal = ProviderError::rpc(-32000, "intrinsic gas too low");
+        assert!(refused::<()>(Err(refusal)));
+
+        // An endpoint that takes the probe does not pass it on to a node
+        assert!(!refused(Ok(TxHash::ZERO)));
+        let rejection = ProviderError::rpc(-32000, "sequencer unavailable");
+        assert!(!refused::<()>(Err(rejection)));
+        let throttled = ProviderError::RateLimited("HTTP 429".into());
+        assert!(!refused::<()>(Err(throttled)));
+    }
+
+    #[tokio::test]
+    async fn realtime_failing_leaves_standard_sends() {
+        let provider = MockProvider::new();
+        provider.set_realtime_support(true);
+        assert_eq!(probe(&provider).await.realtime, Some(true));
+
+        provider.set_fault_plan(Arc::new(FaultPlan::new(0).fail_rate(
+            Method::SendRealtime,
+            1.0,
+            Fault::Timeout,
+        )));
+        let capabilities = probe(&provider).await;
+        assert_eq!(capabilities.realtime, Some(false));
+        assert_eq!(capabilities.tier(), DegradationTier::Normal);
+    }
+
+    #[tokio::test]
+    async fn lost_sends_fail_probes_that_pass() {
+        let settings = DegradationConfig {
+            enabled: true,
+            degrade_after_probes: 1,
+            recover_after_probes: 1,
+            ..DegradationConfig::default()
+        };
+        let pool = ProviderPool::single("up", Arc::new(MockProvider::new()));
+        let start = Utc::now();
+        let mut monitor = DegradationMonitor::new(&settings, start);
+
+        let dropped = ActionResult::dropped(TxHash::ZERO, "not mined in time");
+        monitor.record_result(&dropped);
+        monitor.record_result(&dropped);
+        monitor.record_result(&ActionResult::with_status(ActionStatus::Succeeded));
+        let change = monitor.probe_if_due(&pool, start).await.unwrap();
+        assert_eq!(change.to, DegradationTier::ReadOnly);
+        assert!(!change.capabilities.sends);
+
+        // Nothing is sent while read-only, so the probe decides again
+        let change = monitor.probe_if_due(&pool, monitor.next_probe()).await;
+        assert_eq!(change.unwrap().to, DegradationTier::Normal);
+    }
+
+    #[tokio::test]
+    async fn pools_can_do_what_any_endpoint_can() {
+        let down = Arc::new(MockProvider::new());
+        down.set_fault_plan(Arc::new(FaultPlan::new(0).fail_rate(
+            Method::SendRawTransaction,
+            1.0,
+            Fault::Timeout,
+        )));
+        let pool = ProviderPool::single("down", Arc::clone(&down));
+        assert_eq!(probe_pool(&pool).await.tier(), DegradationTier::ReadOnly);
+
+        let endpoints = vec![
+            ("down".to_string(), 1, down),
+            ("up".to_string(), 1, Arc::new(MockProvider::new())),
+        ];
+        let pool = ProviderPool::new(AssignmentStrategy::Sticky, endpoints).unwrap();
+        assert_eq!(probe_pool(&pool).await.tier(), DegradationTier::Normal);
+    }
+}
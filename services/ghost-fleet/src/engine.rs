@@ -1,19 +1,355 @@
 //! Behavior engine for coordinating plugin decisions.
 //!
 //! The behavior engine is responsible for:
-//! - Selecting which plugin should act for a given wallet
-//! - Providing context for decision-making (RNG, timestamp, config)
+//! - Collecting candidate actions from every enabled plugin
+//! - Selecting which plugin should act for a given wallet, according to the
+//!   configured [`SelectionStrategy`]
+//! - Providing context for decision-making (RNG, timestamp, config, cooldowns)
+//! - Skipping plugins that cannot possibly act for the wallet, as counted in
+//!   the [`FleetMetrics`]
+//! - Rejecting actions that are still on cooldown, even if a plugin ignores it
+//! - Rejecting all but exit actions of retiring wallets
+//! - Leaving out plugins that fail too often, or that an operator disabled,
+//!   and probing them for recovery with one wallet at a time
+//! - Executing the chosen action, retrying transient errors in place
+//! - Cancelling plugin calls that run past their timeout, and holding the
+//!   nonce of an execution that may have sent its transaction
+//! - Replacing transactions that are not mined in time
+//! - Sweeping what retiring wallets hold left over to their sweep address
+//! - Submitting its own transactions, replacements, cancels and sweeps,
+//!   through the chain's realtime API where the provider supports it; the
+//!   transactions of executed actions are left to their plugins
+//! - Confirming the fleet's [lease](crate::leader) right before each
+//!   transaction is sent, when the fleet runs active-passive
 //! - Recording metrics for actions
 
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-use chrono::Utc;
-use fleet_core::plugins::{Action, ActionPlugin, PluginContext, PluginRegistry};
+use alloy::primitives::{Address, Bytes, TxHash, U256};
+use alloy::sol_types::SolCall;
+use chrono::{DateTime, Utc};
+use evm_provider::{
+    ChainProvider, ExtendedChainProvider, TransactionReceipt, TransactionRequest,
+    TransactionSigner,
+};
+use fleet_core::clock::{SharedClock, system_clock};
+use fleet_core::plugins::{
+    Action, ActionCooldowns, ActionId, ActionPlugin, ActionResult, ActionStatus, Admission,
+    DataFlow, HealthSettings, PendingTx, PluginActivity, PluginCall, PluginContext, PluginHealth,
+    PluginHealthStatus, PluginId, PluginOverride, PluginRegistry, PluginSelector,
+    ReplacementOutcome, SelectionStrategy, Submission, SubmissionPath, checked_action_id,
+};
+use fleet_core::{ErrorClass, FleetError};
+use fleet_core::metrics::FleetMetrics;
 use fleet_core::profiles::BehaviorProfile;
+use fleet_core::sensitive::Sensitive;
 use fleet_core::wallet::WalletState;
 use rand::rngs::StdRng;
 use rand::SeedableRng;
-use tracing::{debug, instrument};
+use tracing::{debug, info, instrument, warn};
+
+use crate::leader::Leadership;
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// RETRY POLICY
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// In-place retries of actions that fail with a
+/// [transient](ErrorClass::Transient) error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt.
+    pub retries: u32,
+
+    /// Pause before each retry.
+    pub delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            retries: 2,
+            delay: Duration::from_millis(500),
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// ACTION TIMEOUTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// How long plugins may take to decide and to execute an action.
+///
+/// A call that takes longer is cancelled and fails with a
+/// [transient](ErrorClass::Transient) [`FleetError::PluginTimeout`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActionTimeouts {
+    /// Time to decide an action.
+    pub decide: Duration,
+
+    /// Time to execute an action.
+    pub execute: Duration,
+
+    /// Decide timeouts of specific plugins, overriding `decide`.
+    pub plugin_decide: HashMap<PluginId, Duration>,
+
+    /// Execute timeouts of specific plugins, overriding `execute`.
+    pub plugin_execute: HashMap<PluginId, Duration>,
+}
+
+impl Default for ActionTimeouts {
+    fn default() -> Self {
+        Self {
+            decide: Duration::from_secs(10),
+            execute: Duration::from_secs(120),
+            plugin_decide: HashMap::new(),
+            plugin_execute: HashMap::new(),
+        }
+    }
+}
+
+impl ActionTimeouts {
+    /// Time a plugin has to decide an action.
+    #[must_use]
+    pub fn decide_timeout(&self, plugin_id: &str) -> Duration {
+        self.plugin_decide
+            .get(plugin_id)
+            .copied()
+            .unwrap_or(self.decide)
+    }
+
+    /// Time a plugin has to execute an action.
+    #[must_use]
+    pub fn execute_timeout(&self, plugin_id: &str) -> Duration {
+        self.plugin_execute
+            .get(plugin_id)
+            .copied()
+            .unwrap_or(self.execute)
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// REPLACEMENT POLICY
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Gas limit of a plain transfer, used by cancel transactions.
+const TRANSFER_GAS: u64 = 21_000;
+
+/// Replacement of transactions that are sent but not mined in time.
+///
+/// Once an action's receipt timeout passes, the engine asks the plugin for a
+/// replacement with the same nonce and a higher fee, or cancels the
+/// transaction with a self-transfer if the plugin declines. Each replacement
+/// gets the same timeout; after `max_replacements` the transaction is
+/// abandoned.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplacementPolicy {
+    /// How long to wait for a receipt before replacing a transaction.
+    pub receipt_timeout: Duration,
+
+    /// Receipt timeouts of specific actions, overriding `receipt_timeout`.
+    pub action_timeouts: HashMap<ActionId, Duration>,
+
+    /// Replacements to send before abandoning a transaction.
+    pub max_replacements: u32,
+
+    /// Fee increase of each replacement over the previous one, in percent.
+    pub fee_bump_pct: u32,
+}
+
+impl Default for ReplacementPolicy {
+    fn default() -> Self {
+        Self {
+            receipt_timeout: Duration::from_secs(60),
+            action_timeouts: HashMap::new(),
+            max_replacements: 3,
+            fee_bump_pct: 12,
+        }
+    }
+}
+
+impl ReplacementPolicy {
+    /// Receipt timeout of an action.
+    #[must_use]
+    pub fn receipt_timeout(&self, action: &ActionId) -> Duration {
+        self.action_timeouts
+            .get(action)
+            .copied()
+            .unwrap_or(self.receipt_timeout)
+    }
+
+    /// Fee increase of the `attempt`th replacement over the original, in
+    /// percent.
+    ///
+    /// Bumps compound and round up, so every replacement pays at least
+    /// `fee_bump_pct` more than the one before.
+    #[must_use]
+    pub fn bump_for(&self, attempt: u32) -> u32 {
+        let factor = 100 + u64::from(self.fee_bump_pct);
+        let total = (0..attempt).fold(100_u64, |total, _| (total * factor).div_ceil(100));
+        u32::try_from(total - 100).unwrap_or(u32::MAX)
+    }
+}
+
+/// Raise `fee` by `bump_pct` percent, rounding up.
+fn bump_fee(fee: u128, bump_pct: u32) -> u128 {
+    fee.saturating_mul(100 + u128::from(bump_pct)).div_ceil(100)
+}
+
+/// Sign a zero-value transfer to the signer itself, replacing `original`.
+///
+/// Priced `bump_pct` above the original's gas price or the current one,
+/// whichever is higher, as a node only accepts a replacement paying more than
+/// the original even after the gas price has fallen.
+async fn cancel_transaction(
+    chain: &dyn ChainProvider,
+    signer: &dyn TransactionSigner,
+    original: &PendingTx,
+    bump_pct: u32,
+) -> anyhow::Result<(Sensitive<Bytes>, u128)> {
+    let current = chain.gas_price().await?;
+    let fee = bump_fee(original.gas_price.map_or(current, |fee| fee.max(current)), bump_pct);
+    let request = TransactionRequest::new()
+        .to(signer.address())
+        .value(U256::ZERO)
+        .nonce(original.nonce)
+        .gas_limit(TRANSFER_GAS)
+        .max_fee_per_gas(fee)
+        .max_priority_fee_per_gas(fee);
+    let raw = signer.sign_transaction(&request, chain.chain_id()).await?;
+    Ok((Sensitive::new(raw), fee))
+}
+
+/// Transaction of a result that was sent but not mined in time.
+fn pending_tx(result: &ActionResult) -> Option<TxHash> {
+    let dropped = result.status == ActionStatus::Dropped && result.is_retryable();
+    result.tx_hash.filter(|_| dropped && result.replacement.is_none())
+}
+
+/// Whether a transaction from `wallet` with its nonce may be pending or
+/// mined: the chain counts one, or cannot be asked.
+async fn may_have_sent(chain: &dyn ChainProvider, wallet: &WalletState) -> bool {
+    match chain.get_pending_nonce(wallet.address).await {
+        Ok(pending) => pending > wallet.nonce,
+        Err(e) => {
+            warn!(error = %e, "Failed to read the pending nonce, assuming the worst");
+            true
+        }
+    }
+}
+
+/// Transactions sent with one nonce, and what their being mined means.
+#[derive(Debug, Default)]
+struct SameNonce {
+    /// Sent transactions, original first.
+    sent: Vec<(TxHash, ReplacementOutcome, Option<u128>)>,
+}
+
+impl SameNonce {
+    /// The first sent transaction that has been mined, if any.
+    async fn mined(
+        &self,
+        chain: &dyn ChainProvider,
+    ) -> Option<(TransactionReceipt, ReplacementOutcome, Option<u128>)> {
+        for &(tx_hash, outcome, fee) in &self.sent {
+            if let Ok(receipt) = chain.wait_for_receipt(tx_hash, Duration::ZERO).await {
+                return Some((receipt, outcome, fee));
+            }
+        }
+        None
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// SUBMISSION
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// A signed transaction the engine submitted.
+#[derive(Debug)]
+struct Submitted {
+    /// Hash of the transaction.
+    tx_hash: TxHash,
+
+    /// Its receipt, if it was mined in time.
+    receipt: Option<TransactionReceipt>,
+
+    /// How it was submitted.
+    submission: Submission,
+}
+
+/// Whole milliseconds of `duration`, saturating.
+fn millis(duration: Duration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// SWEEPS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// ID of the plugin-free actions the engine runs itself, as in the metrics.
+pub const ENGINE_ID: &str = "fleet";
+
+/// Action ID of a sweep, see [`BehaviorEngine::sweep`].
+pub const ACTION_SWEEP: &str = checked_action_id("fleet.sweep");
+
+/// Gas limit of a token transfer sent by a sweep.
+const TOKEN_TRANSFER_GAS: u64 = 100_000;
+
+alloy::sol! {
+    /// The one ERC-20 call a sweep makes.
+    function transfer(address to, uint256 amount) external returns (bool);
+}
+
+/// What a [sweep](BehaviorEngine::sweep) moves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SweepAsset {
+    /// The native balance, less the transfer's own gas.
+    Native,
+    /// The whole balance of an ERC-20 token.
+    Token(Address),
+}
+
+/// Sign a transfer of what the wallet holds of `asset` to `to`, priced at
+/// the current gas price.
+///
+/// Returns `None` if there is nothing to move, which for the native balance
+/// means it does not cover the transfer's gas.
+async fn sweep_transaction(
+    chain: &dyn ChainProvider,
+    signer: &dyn TransactionSigner,
+    wallet: &WalletState,
+    asset: SweepAsset,
+    to: Address,
+) -> anyhow::Result<Option<(Sensitive<Bytes>, u128)>> {
+    let fee = chain.gas_price().await?;
+    let (gas_limit, call, value, input) = match asset {
+        SweepAsset::Native => {
+            let gas_cost = U256::from(fee).saturating_mul(U256::from(TRANSFER_GAS));
+            let value = wallet.native_balance.saturating_sub(gas_cost);
+            (TRANSFER_GAS, to, value, Bytes::new())
+        }
+        SweepAsset::Token(token) => {
+            let amount = wallet.token_balance(token);
+            let input = transferCall { to, amount }.abi_encode();
+            (TOKEN_TRANSFER_GAS, token, amount, input.into())
+        }
+    };
+    if value.is_zero() {
+        return Ok(None);
+    }
+    let request = TransactionRequest::new()
+        .to(call)
+        .value(if asset == SweepAsset::Native { value } else { U256::ZERO })
+        .data(input)
+        .nonce(wallet.nonce)
+        .gas_limit(gas_limit)
+        .max_fee_per_gas(fee)
+        .max_priority_fee_per_gas(fee);
+    let raw = signer.sign_transaction(&request, chain.chain_id()).await?;
+    Ok(Some((Sensitive::new(raw), fee)))
+}
 
 // ═══════════════════════════════════════════════════════════════════════════════
 // BEHAVIOR ENGINE
@@ -22,40 +358,238 @@ use tracing::{debug, instrument};
 /// Coordinates plugin decisions for wallet actions.
 #[derive(Debug)]
 pub struct BehaviorEngine {
+    /// Enabled plugins and their priorities.
+    registry: PluginRegistry,
+
     /// Enabled plugins in priority order.
     plugins: Vec<Arc<dyn ActionPlugin>>,
 
+    /// Enabled plugin IDs in priority order.
+    order: Vec<PluginId>,
+
+    /// Chooses between candidate actions.
+    selector: PluginSelector,
+
     /// Random number generator.
     rng: StdRng,
 
     /// Plugin-specific configuration.
     plugin_config: serde_json::Value,
+
+    /// Source of the decision timestamp.
+    clock: SharedClock,
+
+    /// Retries of transient execution errors.
+    retry: RetryPolicy,
+
+    /// How long plugin calls may take.
+    timeouts: ActionTimeouts,
+
+    /// Nonce each wallet's timed-out execution may have sent a transaction
+    /// with, until taken.
+    nonces_in_doubt: HashMap<String, u64>,
+
+    /// Replacement of transactions that are not mined in time.
+    replacement: ReplacementPolicy,
+
+    /// Where prefiltered plugins are counted.
+    metrics: Arc<FleetMetrics>,
+
+    /// Error rates of the plugins, and which are disabled.
+    health: PluginHealth,
+
+    /// Whether to skip the realtime API even where it is supported.
+    force_standard_submission: bool,
+
+    /// Whether the realtime API was last found failing, so that it is
+    /// skipped until it works again.
+    realtime_down: bool,
+
+    /// Lease that must be held to send transactions, if any.
+    leadership: Option<Arc<Leadership>>,
 }
 
 impl BehaviorEngine {
     /// Create a new behavior engine with the given plugins.
     #[must_use]
-    pub fn new(registry: &PluginRegistry, enabled_ids: &[String]) -> Self {
-        let plugins = registry.enabled(enabled_ids);
+    pub fn new(
+        registry: &PluginRegistry,
+        enabled_ids: &[String],
+        strategy: SelectionStrategy,
+    ) -> Self {
+        Self::with_rng(registry, enabled_ids, strategy, StdRng::from_os_rng())
+    }
+
+    /// Create a behavior engine with a seeded RNG (for testing and simulation).
+    #[must_use]
+    pub fn with_seed(
+        registry: &PluginRegistry,
+        enabled_ids: &[String],
+        strategy: SelectionStrategy,
+        seed: u64,
+    ) -> Self {
+        Self::with_rng(registry, enabled_ids, strategy, StdRng::seed_from_u64(seed))
+    }
+
+    fn with_rng(
+        registry: &PluginRegistry,
+        enabled_ids: &[String],
+        strategy: SelectionStrategy,
+        rng: StdRng,
+    ) -> Self {
+        let registry = registry.subset(enabled_ids);
         Self {
-            plugins,
-            rng: StdRng::from_os_rng(),
+            plugins: registry.ordered_plugins(),
+            order: registry.plugin_ids(),
+            registry,
+            selector: PluginSelector::new(strategy),
+            rng,
             plugin_config: serde_json::Value::Null,
+            clock: system_clock(),
+            retry: RetryPolicy::default(),
+            timeouts: ActionTimeouts::default(),
+            nonces_in_doubt: HashMap::new(),
+            replacement: ReplacementPolicy::default(),
+            metrics: Arc::default(),
+            health: PluginHealth::new(HealthSettings::default()),
+            force_standard_submission: false,
+            realtime_down: false,
+            leadership: None,
         }
     }
 
-    /// Create a behavior engine with a seeded RNG (for testing).
+    /// Use `clock` for decision timestamps instead of the system clock.
     #[must_use]
-    #[expect(dead_code, reason = "public API for deterministic testing")]
-    pub fn with_seed(registry: &PluginRegistry, enabled_ids: &[String], seed: u64) -> Self {
-        let plugins = registry.enabled(enabled_ids);
-        Self {
-            plugins,
-            rng: StdRng::seed_from_u64(seed),
-            plugin_config: serde_json::Value::Null,
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Retry transient execution errors according to `retry`.
+    #[must_use]
+    pub const fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Cancel plugin calls that take longer than `timeouts` allow.
+    #[must_use]
+    pub fn with_action_timeouts(mut self, timeouts: ActionTimeouts) -> Self {
+        self.timeouts = timeouts;
+        self
+    }
+
+    /// Replace transactions that are not mined in time according to
+    /// `replacement`.
+    #[must_use]
+    pub fn with_replacement_policy(mut self, replacement: ReplacementPolicy) -> Self {
+        self.replacement = replacement;
+        self
+    }
+
+    /// Count prefiltered plugins in `metrics` instead of a private collector.
+    #[must_use]
+    pub fn with_metrics(mut self, metrics: Arc<FleetMetrics>) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    /// Disable plugins that fail too often, and probe them, according to
+    /// `settings`.
+    #[must_use]
+    pub fn with_plugin_health(mut self, settings: HealthSettings) -> Self {
+        self.health = PluginHealth::new(settings);
+        self
+    }
+
+    /// Submit transactions the standard way even where the chain offers a
+    /// realtime API, for debugging.
+    #[must_use]
+    pub const fn with_force_standard_submission(mut self, force: bool) -> Self {
+        self.force_standard_submission = force;
+        self
+    }
+
+    /// Submit transactions the standard way while the realtime API is
+    /// `down`, as the degradation probes find it.
+    pub fn set_realtime_down(&mut self, down: bool) {
+        if down != self.realtime_down {
+            if down {
+                warn!("Realtime API failing, submitting the standard way");
+            } else {
+                info!("Realtime API recovered");
+            }
+        }
+        self.realtime_down = down;
+    }
+
+    /// Send transactions only while `leadership` holds the fleet's lease,
+    /// confirmed right before each one.
+    #[must_use]
+    pub fn with_leadership(mut self, leadership: Arc<Leadership>) -> Self {
+        self.leadership = Some(leadership);
+        self
+    }
+
+    /// Check that this node may send a transaction for the fleet now.
+    async fn may_send(&self) -> bool {
+        match &self.leadership {
+            Some(leadership) => leadership.confirm().await,
+            None => true,
         }
     }
 
+    /// Submit the signed transaction `raw` and wait up to the `timeout` for
+    /// its receipt.
+    ///
+    /// Only replacements, cancels and sweeps are sent through here; plugins
+    /// send the transactions of the actions they execute themselves.
+    ///
+    /// Where the chain supports it, it is not
+    /// [forced off](Self::with_force_standard_submission) and not
+    /// [down](Self::set_realtime_down), the transaction
+    /// goes through the realtime API and the receipt it answers with is
+    /// final. A realtime submission that fails with a retryable error is sent
+    /// the standard way instead, and polled for.
+    async fn submit(
+        &self,
+        chain: &dyn ExtendedChainProvider,
+        raw: Sensitive<Bytes>,
+        timeout: Duration,
+    ) -> evm_provider::Result<Submitted> {
+        if !self.force_standard_submission && !self.realtime_down && chain.supports_realtime() {
+            let started = Instant::now();
+            match chain.send_realtime(raw.expose().clone()).await {
+                Ok(receipt) => {
+                    return Ok(Submitted {
+                        tx_hash: receipt.tx_hash,
+                        receipt: Some(receipt),
+                        submission: Submission {
+                            path: SubmissionPath::Realtime,
+                            receipt_latency_ms: Some(millis(started.elapsed())),
+                        },
+                    });
+                }
+                Err(e) if e.is_retryable() => {
+                    warn!(error = %e, "Realtime submission failed, sending the standard way");
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        let started = Instant::now();
+        let tx_hash = chain.send_raw_transaction(raw.into_inner()).await?;
+        let receipt = chain.wait_for_receipt(tx_hash, timeout).await.ok();
+        Ok(Submitted {
+            tx_hash,
+            submission: Submission {
+                path: SubmissionPath::Standard,
+                receipt_latency_ms: receipt.as_ref().map(|_| millis(started.elapsed())),
+            },
+            receipt,
+        })
+    }
+
     /// Set plugin-specific configuration.
     #[expect(dead_code, reason = "public API for plugin configuration")]
     pub fn set_plugin_config(&mut self, config: serde_json::Value) {
@@ -64,9 +598,19 @@ impl BehaviorEngine {
 
     /// Decide what action (if any) a wallet should take.
     ///
-    /// Iterates through enabled plugins in priority order, asking each
-    /// to decide an action. Returns the first action decided, along with
-    /// the plugin that decided it.
+    /// Asks every enabled plugin to decide an action, then lets the
+    /// [`SelectionStrategy`] choose one of the candidates. Actions still on
+    /// cooldown for the wallet are rejected before selection, and a plugin
+    /// that fails to decide does not keep the others from being considered.
+    ///
+    /// A plugin that does not decide within its
+    /// [timeout](ActionTimeouts::decide_timeout) is cancelled and counts as
+    /// failed.
+    ///
+    /// Disabled plugins are not asked, so the wallet falls through to the
+    /// others or skips, unless it is the one to [probe](Admission::Probe) a
+    /// plugin. A probed plugin whose action is selected is judged by the
+    /// action's execution.
     ///
     /// # Arguments
     ///
@@ -82,34 +626,422 @@ impl BehaviorEngine {
         wallet: &WalletState,
         profile: &BehaviorProfile,
     ) -> Option<(Arc<dyn ActionPlugin>, Action)> {
-        let mut context = PluginContext::new(Utc::now(), &mut self.rng, &self.plugin_config);
+        let now = self.clock.now();
+        let mut probes = Vec::new();
+        let mut disabled = Vec::new();
+        for plugin_id in &self.order {
+            match self.health.admit(plugin_id, now) {
+                Admission::Enabled => {}
+                Admission::Probe => probes.push(plugin_id.clone()),
+                Admission::Disabled => disabled.push(plugin_id.clone()),
+            }
+        }
 
-        for plugin in &self.plugins {
-            debug!(plugin_id = plugin.id(), "Checking plugin for action");
+        let cooldowns = ActionCooldowns::resolve(&self.plugins, profile, wallet);
+        let mut context =
+            PluginContext::new(now, &mut self.rng, &self.plugin_config).with_cooldowns(cooldowns);
 
-            match plugin.decide_action(wallet, profile, &mut context).await {
-                Ok(Some(action)) => {
-                    debug!(
-                        plugin_id = plugin.id(),
+        let timeouts = &self.timeouts;
+        let decisions = self
+            .registry
+            .decide_except(wallet, profile, &mut context, &disabled, |plugin_id| {
+                Some(timeouts.decide_timeout(plugin_id))
+            })
+            .await;
+        for plugin_id in &decisions.timed_out {
+            self.metrics.record_timeout(plugin_id);
+        }
+        for plugin_id in &decisions.prefiltered {
+            self.metrics.record_prefiltered(plugin_id);
+            if probes.contains(plugin_id) {
+                self.health.cancel_probe(plugin_id);
+            }
+        }
+        for (plugin_id, class) in &decisions.failed {
+            self.health.record_error(plugin_id, PluginCall::Decide, *class, now);
+        }
+        let decided: Vec<PluginId> = decisions
+            .candidates
+            .iter()
+            .map(|(plugin_id, _, _)| plugin_id.clone())
+            .chain(decisions.declined)
+            .collect();
+        let mut candidates = decisions.candidates;
+        candidates.retain(|(plugin_id, action, _)| {
+            let remaining = context.cooldowns.remaining(action.id.as_str(), context.now);
+            if let Some(remaining) = remaining {
+                warn!(
+                    plugin_id = %plugin_id,
+                    action_id = %action.id,
+                    remaining_secs = remaining.num_seconds(),
+                    "Plugin decided an action on cooldown, rejecting"
+                );
+            }
+            remaining.is_none()
+        });
+        if wallet.is_retiring() {
+            let registry = &self.registry;
+            candidates.retain(|(plugin_id, action, _)| {
+                let exit = action.is_refresh_request()
+                    || registry.get(plugin_id).is_some_and(|p| p.is_exit_action(&action.id));
+                if !exit {
+                    warn!(
+                        plugin_id = %plugin_id,
                         action_id = %action.id,
-                        "Plugin decided action"
+                        "Plugin decided a non-exit action for a retiring wallet, rejecting"
                     );
-                    return Some((Arc::clone(plugin), action));
                 }
-                Ok(None) => {
-                    debug!(plugin_id = plugin.id(), "Plugin decided no action");
+                exit
+            });
+        }
+
+        let candidate_count = candidates.len();
+        let selected = self
+            .selector
+            .select(&wallet.id, candidates, &self.order, &mut *context.rng);
+
+        // A probe whose action was selected waits for the execution
+        let awaiting_execution = selected
+            .as_ref()
+            .map(|(plugin_id, _, _)| plugin_id)
+            .filter(|plugin_id| probes.contains(plugin_id));
+        for plugin_id in decided.iter().filter(|id| Some(*id) != awaiting_execution) {
+            self.health.record_success(plugin_id, PluginCall::Decide, now);
+        }
+        let (plugin_id, action, priority) = selected?;
+
+        debug!(
+            plugin_id = %plugin_id,
+            action_id = %action.id,
+            priority,
+            candidates = candidate_count,
+            strategy = ?self.selector.strategy(),
+            "Selected action"
+        );
+        let plugin = self.registry.get(&plugin_id)?;
+        Some((Arc::clone(plugin), action))
+    }
+
+    /// Decide a given action for a wallet, as an operator asked for it.
+    ///
+    /// The plugin offering `action_id` decides as usual; if it picks that
+    /// action its data is kept, otherwise the action is built without data.
+    /// Cooldowns are not enforced, and the plugin may be disabled: the
+    /// operator overrules both.
+    ///
+    /// Returns `None` if no enabled plugin offers the action.
+    #[instrument(skip(self, wallet, profile), fields(wallet_id = %wallet.id))]
+    pub async fn decide_forced(
+        &mut self,
+        wallet: &WalletState,
+        profile: &BehaviorProfile,
+        action_id: &ActionId,
+    ) -> Option<(Arc<dyn ActionPlugin>, Action)> {
+        let plugin = self
+            .plugins
+            .iter()
+            .find(|p| p.available_actions().contains(action_id))
+            .map(Arc::clone)?;
+
+        let cooldowns = ActionCooldowns::resolve(&self.plugins, profile, wallet);
+        let mut context = PluginContext::new(self.clock.now(), &mut self.rng, &self.plugin_config)
+            .with_cooldowns(cooldowns);
+        let limit = self.timeouts.decide_timeout(plugin.id());
+        let deciding = plugin.decide_action(wallet, profile, &mut context);
+        let decided = match tokio::time::timeout(limit, deciding).await {
+            Ok(Ok(decided)) => decided.filter(|action| action.id == *action_id),
+            Ok(Err(e)) => {
+                warn!(plugin_id = plugin.id(), error = %e, "Plugin failed to decide forced action");
+                None
+            }
+            Err(_) => {
+                self.metrics.record_timeout(plugin.id());
+                warn!(plugin_id = plugin.id(), ?limit, "Plugin timed out deciding forced action");
+                None
+            }
+        };
+
+        debug!(action_id = %action_id, with_data = decided.is_some(), "Forced action");
+        let action = decided.unwrap_or_else(|| Action::new(action_id.clone(), action_id.as_str()));
+        Some((plugin, action))
+    }
+
+    /// Execute an action, retrying transient errors in place.
+    ///
+    /// Errors of any other [`ErrorClass`] are returned right away; transient
+    /// ones only once the [`RetryPolicy`] is used up. The final outcome
+    /// counts toward the plugin's health.
+    ///
+    /// An attempt that does not finish within the plugin's
+    /// [timeout](ActionTimeouts::execute_timeout) is cancelled, which is a
+    /// transient error. If `chain` then counts a transaction pending from the
+    /// wallet with `wallet.nonce` or later, or cannot tell, the attempt may
+    /// have sent it: the nonce is [in doubt](Self::take_nonce_in_doubt) and
+    /// the error is returned without retrying, so the nonce is not reused.
+    ///
+    /// # Errors
+    ///
+    /// Returns the plugin's last error if the action could not be executed,
+    /// or [`FleetError::LeaseLost`], not counted, if the node lost the
+    /// fleet's lease before an attempt.
+    #[instrument(skip_all, fields(wallet_id = %wallet.id, action_id = %action.id))]
+    pub async fn execute_action(
+        &mut self,
+        plugin: &dyn ActionPlugin,
+        action: &Action,
+        wallet: &WalletState,
+        chain: &dyn ChainProvider,
+    ) -> fleet_core::Result<ActionResult> {
+        let limit = self.timeouts.execute_timeout(plugin.id());
+        let mut attempt = 0;
+        let result = loop {
+            if !self.may_send().await {
+                warn!("Fleet lease lost, not executing");
+                return Err(FleetError::LeaseLost);
+            }
+            let executing = plugin.execute_action(action, wallet, wallet.nonce);
+            let result = if let Ok(result) = tokio::time::timeout(limit, executing).await {
+                result
+            } else {
+                self.metrics.record_timeout(plugin.id());
+                let error = FleetError::timeout(plugin.id(), "execute", limit);
+                if may_have_sent(chain, wallet).await {
+                    warn!(
+                        nonce = wallet.nonce,
+                        "Execution timed out after it may have sent its transaction, \
+                         holding the nonce"
+                    );
+                    self.nonces_in_doubt.insert(wallet.id.clone(), wallet.nonce);
+                    break Err(error);
                 }
-                Err(e) => {
-                    tracing::warn!(
-                        plugin_id = plugin.id(),
+                Err(error)
+            };
+            match result {
+                Err(e) if e.class() == ErrorClass::Transient && attempt < self.retry.retries => {
+                    attempt += 1;
+                    debug!(
                         error = %e,
-                        "Plugin error during decision"
+                        attempt,
+                        retries = self.retry.retries,
+                        "Transient execution error, retrying"
                     );
+                    tokio::time::sleep(self.retry.delay).await;
                 }
+                result => break result,
+            }
+        };
+
+        let now = self.clock.now();
+        match &result {
+            Ok(_) => self.health.record_success(plugin.id(), PluginCall::Execute, now),
+            Err(e) => {
+                self.health.record_error(plugin.id(), PluginCall::Execute, e.class(), now);
             }
         }
+        result
+    }
 
-        None
+    /// Take the nonce a timed-out [execution](Self::execute_action) for the
+    /// wallet may have sent a transaction with.
+    ///
+    /// The wallet must not send another transaction with it before the
+    /// chain reports it used.
+    pub fn take_nonce_in_doubt(&mut self, wallet_id: &str) -> Option<u64> {
+        self.nonces_in_doubt.remove(wallet_id)
+    }
+
+    /// Settle an executed action whose transaction was not mined in time.
+    ///
+    /// Any other result is returned as is. Otherwise the original gets the
+    /// action's receipt timeout, then is replaced per the
+    /// [`ReplacementPolicy`] with the nonce it was sent with, `wallet.nonce`.
+    /// The result is that of whichever transaction was mined, with its
+    /// [`ReplacementOutcome`]; a cancelled or abandoned action counts as
+    /// dropped. A mined action carries the plugin's
+    /// [state from its receipt](ActionPlugin::state_from_receipt), if any,
+    /// and its [DATA flows](ActionPlugin::data_flows) if it did not revert;
+    /// a mined replacement also carries its [`Submission`]. A node that lost the
+    /// fleet's lease sends no replacement and returns the result as is.
+    #[instrument(skip_all, fields(wallet_id = %wallet.id, action_id = %action.id))]
+    pub async fn settle(
+        &self,
+        plugin: &dyn ActionPlugin,
+        action: &Action,
+        wallet: &WalletState,
+        chain: &dyn ExtendedChainProvider,
+        signer: &dyn TransactionSigner,
+        result: ActionResult,
+    ) -> ActionResult {
+        let Some(tx_hash) = pending_tx(&result) else {
+            return result;
+        };
+        let timeout = self.replacement.receipt_timeout(&action.id);
+        let fee = result.effective_gas_price;
+        let state = |receipt: &TransactionReceipt| {
+            plugin.state_from_receipt(action, wallet, receipt)
+        };
+        let flows = |receipt: &TransactionReceipt| plugin.data_flows(action, wallet, receipt);
+        let mut same_nonce = SameNonce::default();
+        same_nonce.sent.push((tx_hash, ReplacementOutcome::OriginalMined, fee));
+        if let Ok(receipt) = chain.wait_for_receipt(tx_hash, timeout).await {
+            return settled(
+                result,
+                &receipt,
+                ReplacementOutcome::OriginalMined,
+                0,
+                fee,
+                state,
+                flows,
+            );
+        }
+
+        for attempt in 1..=self.replacement.max_replacements {
+            let original = PendingTx {
+                tx_hash,
+                nonce: wallet.nonce,
+                replacements: attempt - 1,
+                gas_price: fee,
+            };
+            let bump = self.replacement.bump_for(attempt);
+            if !self.may_send().await {
+                warn!(%tx_hash, "Fleet lease lost, leaving the transaction unreplaced");
+                return result;
+            }
+            warn!(%tx_hash, attempt, bump_pct = bump, "Transaction not mined in time, replacing");
+
+            let sent = match self
+                .replacement_tx(plugin, action, signer, chain, &original, bump)
+                .await
+            {
+                Some((raw, outcome, fee)) => self
+                    .submit(chain, raw, timeout)
+                    .await
+                    .map(|submitted| (submitted, outcome, fee))
+                    .map_err(|e| warn!(error = %e, attempt, "Failed to send replacement")),
+                None => Err(()),
+            };
+            if let Ok((submitted, outcome, fee)) = sent {
+                same_nonce.sent.push((submitted.tx_hash, outcome, fee));
+                if let Some(receipt) = &submitted.receipt {
+                    return settled(result, receipt, outcome, attempt, fee, state, flows)
+                        .with_submission(submitted.submission);
+                }
+            }
+            // An earlier transaction may have been mined in the meantime
+            if let Some((receipt, outcome, fee)) = same_nonce.mined(chain).await {
+                return settled(result, &receipt, outcome, attempt, fee, state, flows);
+            }
+        }
+
+        warn!(%tx_hash, "No transaction mined after the last replacement, abandoning");
+        result.with_replacement(ReplacementOutcome::Abandoned, self.replacement.max_replacements)
+    }
+
+    /// Build the `original`'s replacement: the plugin's, or a cancel
+    /// transaction if the plugin declines.
+    async fn replacement_tx(
+        &self,
+        plugin: &dyn ActionPlugin,
+        action: &Action,
+        signer: &dyn TransactionSigner,
+        chain: &dyn ChainProvider,
+        original: &PendingTx,
+        bump_pct: u32,
+    ) -> Option<(Sensitive<Bytes>, ReplacementOutcome, Option<u128>)> {
+        match plugin.build_replacement(action, original, bump_pct).await {
+            Ok(Some(raw)) => {
+                let raw = Sensitive::new(raw);
+                return Some((raw, ReplacementOutcome::ReplacementMined, None));
+            }
+            Ok(None) => debug!("Plugin declined to replace, cancelling"),
+            Err(e) => warn!(error = %e, "Plugin failed to build a replacement, cancelling"),
+        }
+        match cancel_transaction(chain, signer, original, bump_pct).await {
+            Ok((raw, fee)) => Some((raw, ReplacementOutcome::Cancelled, Some(fee))),
+            Err(e) => {
+                warn!(error = %e, "Failed to build a cancel transaction");
+                None
+            }
+        }
+    }
+
+    /// Sweep what the wallet holds of `asset` to `to`, with the wallet's
+    /// nonce.
+    ///
+    /// The transfer is not replaced if it is not mined within the sweep's
+    /// receipt timeout; it counts as dropped, and the next sweep of the asset
+    /// reuses the nonce. Returns a [skipped](ActionStatus::Skipped) result if
+    /// there is nothing to move, and otherwise one with the transfer's
+    /// [`Submission`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transfer could not be priced, signed or sent,
+    /// or the node lost the fleet's lease before sending it.
+    #[instrument(skip_all, fields(wallet_id = %wallet.id, asset = ?asset, to = %to))]
+    pub async fn sweep(
+        &self,
+        wallet: &WalletState,
+        chain: &dyn ExtendedChainProvider,
+        signer: &dyn TransactionSigner,
+        asset: SweepAsset,
+        to: Address,
+    ) -> fleet_core::Result<ActionResult> {
+        let transaction = sweep_transaction(chain, signer, wallet, asset, to)
+            .await
+            .map_err(|e| FleetError::plugin(ErrorClass::Transient, e.to_string()))?;
+        let Some((raw, fee)) = transaction else {
+            return Ok(ActionResult::skipped("nothing to sweep"));
+        };
+        let timeout = self.replacement.receipt_timeout(&ActionId::new(ACTION_SWEEP));
+        if !self.may_send().await {
+            warn!("Fleet lease lost, not sweeping");
+            return Err(FleetError::LeaseLost);
+        }
+        let submitted = self.submit(chain, raw, timeout).await?;
+        let mut result = submitted.receipt.as_ref().map_or_else(
+            || ActionResult::dropped(submitted.tx_hash, "sweep not mined in time"),
+            ActionResult::from_receipt,
+        );
+        result.effective_gas_price = Some(fee);
+        Ok(result.with_submission(submitted.submission))
+    }
+
+    /// Check whether any enabled plugin still holds positions or bets open
+    /// for the wallet.
+    #[must_use]
+    pub fn has_open_positions(&self, wallet: &WalletState) -> bool {
+        self.plugins.iter().any(|p| p.has_open_positions(wallet))
+    }
+
+    /// DATA paid to the wallet outside its own transactions, as the enabled
+    /// plugins [report](ActionPlugin::settled_flows) it: by plugin ID, with
+    /// the plugin's ID of each flow.
+    #[must_use]
+    pub fn settled_flows(&self, wallet: &WalletState) -> Vec<(&str, String, DataFlow)> {
+        self.plugins
+            .iter()
+            .flat_map(|p| {
+                let plugin = p.id();
+                p.settled_flows(wallet)
+                    .into_iter()
+                    .map(move |(id, flow)| (plugin, id, flow))
+            })
+            .collect()
+    }
+
+    /// What the wallet has done with the enabled plugins so far, added up.
+    #[must_use]
+    pub fn activity(&self, wallet: &WalletState) -> PluginActivity {
+        let mut total = PluginActivity::default();
+        for activity in self.plugins.iter().filter_map(|p| p.activity(wallet)) {
+            total.staked = total.staked.saturating_add(activity.staked);
+            total.extracted = total.extracted.saturating_add(activity.extracted);
+            total.won = total.won.saturating_add(activity.won);
+            total.lost = total.lost.saturating_add(activity.lost);
+            total.positions.extend(activity.positions);
+        }
+        total
     }
 
     /// Get the list of enabled plugins.
@@ -118,6 +1050,38 @@ impl BehaviorEngine {
         &self.plugins
     }
 
+    /// Force an enabled plugin on or off, or with `None` return it to its
+    /// automatic state.
+    ///
+    /// Returns `false` if no enabled plugin has the ID.
+    pub fn set_plugin_override(
+        &mut self,
+        plugin_id: &str,
+        manual: Option<PluginOverride>,
+    ) -> bool {
+        if !self.order.iter().any(|id| id == plugin_id) {
+            return false;
+        }
+        self.health.set_override(plugin_id, manual);
+        true
+    }
+
+    /// Until when a plugin is left out, if it is disabled now.
+    #[must_use]
+    pub fn plugin_disabled_until(&self, plugin_id: &str) -> Option<DateTime<Utc>> {
+        self.health.disabled_until(plugin_id, self.clock.now())
+    }
+
+    /// Health of the enabled plugins, in priority order.
+    #[must_use]
+    pub fn plugin_health(&self) -> Vec<PluginHealthStatus> {
+        let now = self.clock.now();
+        self.order
+            .iter()
+            .map(|plugin_id| self.health.status(plugin_id, now))
+            .collect()
+    }
+
     /// Get all available actions across enabled plugins.
     #[must_use]
     #[allow(dead_code)] // Used in tests and future API consumers
@@ -129,20 +1093,1151 @@ impl BehaviorEngine {
     }
 }
 
+/// Result of an action whose transaction, or one replacing it, was mined.
+fn settled(
+    original: ActionResult,
+    receipt: &TransactionReceipt,
+    outcome: ReplacementOutcome,
+    attempts: u32,
+    fee: Option<u128>,
+    state: impl Fn(&TransactionReceipt) -> Option<serde_json::Value>,
+    flows: impl Fn(&TransactionReceipt) -> Vec<DataFlow>,
+) -> ActionResult {
+    let mut result = if outcome == ReplacementOutcome::Cancelled {
+        let mut cancelled =
+            ActionResult::dropped(receipt.tx_hash, "cancelled after not being mined in time")
+                .with_gas_used(receipt.gas_used)
+                .with_block_number(receipt.block_number);
+        cancelled.effective_gas_price = fee;
+        cancelled
+    } else {
+        let mut mined = ActionResult::from_receipt(receipt);
+        mined.effective_gas_price = fee;
+        mined.gas_estimate = original.gas_estimate;
+        match state(receipt) {
+            Some(state) if receipt.success => mined.with_plugin_state(state),
+            _ => mined,
+        }
+    };
+    if receipt.success && outcome != ReplacementOutcome::Cancelled {
+        result = result.with_data_flows(flows(receipt));
+    }
+    result = result.with_replacement(outcome, attempts);
+    result.with_detail(original.detail)
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // TESTS
 // ═══════════════════════════════════════════════════════════════════════════════
 
 #[cfg(test)]
+#[allow(clippy::unwrap_used)]
 mod tests {
+    use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+
+    use alloy::consensus::{SignableTransaction, TxEip1559, TxEnvelope};
+    use alloy::eips::Encodable2718;
+    use alloy::primitives::{Address, TxKind};
+    use alloy::signers::SignerSync;
+    use alloy::signers::local::PrivateKeySigner;
+    use async_trait::async_trait;
+    use chrono::Utc;
+    use evm_provider::LocalSigner;
+    use fleet_core::FleetError;
+    use fleet_core::plugins::{ActionRequirements, PluginState};
+
     use super::*;
 
+    /// Plugin that always wants to act, ignoring cooldowns.
+    #[derive(Debug)]
+    struct EagerPlugin {
+        id: String,
+        action: &'static str,
+        fails: AtomicBool,
+        /// Whether acting needs gas money.
+        needs_gas: bool,
+        /// Whether its action only gets out of something.
+        exits: bool,
+        decided: AtomicU32,
+    }
+
+    impl EagerPlugin {
+        fn new(id: &str, action: &'static str) -> Self {
+            Self {
+                id: id.to_string(),
+                action,
+                fails: AtomicBool::new(false),
+                needs_gas: false,
+                exits: false,
+                decided: AtomicU32::new(0),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl ActionPlugin for EagerPlugin {
+        fn id(&self) -> &str {
+            &self.id
+        }
+
+        fn name(&self) -> &str {
+            &self.id
+        }
+
+        fn available_actions(&self) -> Vec<ActionId> {
+            vec![ActionId::new(self.action)]
+        }
+
+        fn default_cooldown(&self, _action: &ActionId) -> Option<chrono::Duration> {
+            Some(chrono::Duration::minutes(10))
+        }
+
+        fn is_exit_action(&self, _action: &ActionId) -> bool {
+            self.exits
+        }
+
+        fn requirements(&self, _action: &ActionId) -> ActionRequirements {
+            if self.needs_gas {
+                ActionRequirements::none().with_native_balance(U256::from(1))
+            } else {
+                ActionRequirements::none()
+            }
+        }
+
+        async fn decide_action(
+            &self,
+            _wallet: &WalletState,
+            _profile: &BehaviorProfile,
+            _context: &mut PluginContext<'_>,
+        ) -> fleet_core::Result<Option<Action>> {
+            self.decided.fetch_add(1, Ordering::Relaxed);
+            if self.fails.load(Ordering::Relaxed) {
+                return Err(FleetError::plugin(ErrorClass::Permanent, "broken"));
+            }
+            Ok(Some(Action::new(self.action, self.action)))
+        }
+
+        async fn execute_action(
+            &self,
+            _action: &Action,
+            _wallet: &WalletState,
+            _nonce: u64,
+        ) -> fleet_core::Result<ActionResult> {
+            Ok(ActionResult::failure("eager"))
+        }
+
+        async fn read_state(&self, _address: Address) -> fleet_core::Result<serde_json::Value> {
+            Ok(serde_json::Value::Null)
+        }
+    }
+
+    fn engine(plugins: &[(&str, &'static str)]) -> BehaviorEngine {
+        let mut registry = PluginRegistry::new();
+        for (id, action) in plugins {
+            registry.register(Arc::new(EagerPlugin::new(id, action))).unwrap();
+        }
+        let ids: Vec<_> = plugins.iter().map(|(id, _)| (*id).to_string()).collect();
+        BehaviorEngine::new(&registry, &ids, SelectionStrategy::HighestPriority)
+    }
+
+    /// Engine over plugin "low" (priority 100) and "high" (priority 300).
+    fn prioritized_engine(strategy: SelectionStrategy) -> BehaviorEngine {
+        let mut registry = PluginRegistry::new();
+        registry.register_with_priority(Arc::new(EagerPlugin::new("low", "low.act")), 100).unwrap();
+        registry
+            .register_with_priority(Arc::new(EagerPlugin::new("high", "high.act")), 300)
+            .unwrap();
+        BehaviorEngine::with_seed(&registry, &["low".into(), "high".into()], strategy, 42)
+    }
+
+    async fn chosen(engine: &mut BehaviorEngine, wallet: &WalletState) -> String {
+        let profile = BehaviorProfile::new("test");
+        let (plugin, _) = engine.decide_action(wallet, &profile).await.unwrap();
+        plugin.id().to_string()
+    }
+
     #[test]
     fn engine_with_empty_registry() {
         let registry = PluginRegistry::new();
-        let engine = BehaviorEngine::new(&registry, &[]);
+        let engine = BehaviorEngine::new(&registry, &[], SelectionStrategy::default());
 
         assert!(engine.plugins().is_empty());
         assert!(engine.available_actions().is_empty());
     }
+
+    #[tokio::test]
+    async fn rejects_actions_on_cooldown() {
+        let mut engine = engine(&[("eager", "eager.bet")]);
+        let profile = BehaviorProfile::new("test");
+        let mut wallet = WalletState::new("test".into(), Address::ZERO);
+
+        let (_, action) = engine.decide_action(&wallet, &profile).await.unwrap();
+        assert_eq!(action.id.as_str(), "eager.bet");
+
+        // The plugin picks the same action again, but it is on cooldown
+        wallet.record_execution(action.id, Utc::now());
+        assert!(engine.decide_action(&wallet, &profile).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn falls_through_to_next_plugin_on_cooldown() {
+        let mut engine = engine(&[("eager", "eager.bet"), ("other", "other.stake")]);
+        let profile = BehaviorProfile::new("test");
+        let mut wallet = WalletState::new("test".into(), Address::ZERO);
+        wallet.record_execution("eager.bet".into(), Utc::now());
+
+        let (plugin, action) = engine.decide_action(&wallet, &profile).await.unwrap();
+
+        assert_eq!(plugin.id(), "other");
+        assert_eq!(action.id.as_str(), "other.stake");
+    }
+
+    #[tokio::test]
+    async fn prefilters_plugins_that_cannot_act() {
+        let plugin = Arc::new(EagerPlugin {
+            needs_gas: true,
+            ..EagerPlugin::new("gassy", "gassy.bet")
+        });
+        let mut registry = PluginRegistry::new();
+        registry.register(Arc::clone(&plugin) as Arc<dyn ActionPlugin>).unwrap();
+        let metrics = Arc::new(FleetMetrics::new());
+        let mut engine =
+            BehaviorEngine::new(&registry, &["gassy".into()], SelectionStrategy::default())
+                .with_metrics(Arc::clone(&metrics));
+        let profile = BehaviorProfile::new("test");
+        let mut wallet = WalletState::new("test".into(), Address::ZERO);
+
+        // Without gas money the plugin is never asked
+        assert!(engine.decide_action(&wallet, &profile).await.is_none());
+        assert_eq!(plugin.decided.load(Ordering::Relaxed), 0);
+        assert_eq!(metrics.prefiltered_for_plugin("gassy"), 1);
+
+        wallet.native_balance = U256::from(1);
+        let (_, action) = engine.decide_action(&wallet, &profile).await.unwrap();
+        assert_eq!(action.id.as_str(), "gassy.bet");
+        assert_eq!(plugin.decided.load(Ordering::Relaxed), 1);
+        assert_eq!(metrics.prefiltered_for_plugin("gassy"), 1);
+    }
+
+    #[tokio::test]
+    async fn retiring_wallets_only_take_exits() {
+        let mut registry = PluginRegistry::new();
+        let exit = EagerPlugin {
+            exits: true,
+            ..EagerPlugin::new("exit", "exit.extract")
+        };
+        registry.register_with_priority(Arc::new(exit), 100).unwrap();
+        registry
+            .register_with_priority(Arc::new(EagerPlugin::new("enter", "enter.stake")), 300)
+            .unwrap();
+        let ids = ["exit".into(), "enter".into()];
+        let mut both = BehaviorEngine::new(&registry, &ids, SelectionStrategy::HighestPriority);
+        let mut wallet = WalletState::new("test".into(), Address::ZERO);
+        assert_eq!(chosen(&mut both, &wallet).await, "enter");
+
+        wallet.start_retiring(None, Utc::now());
+        assert_eq!(chosen(&mut both, &wallet).await, "exit");
+        let mut entering = engine(&[("enter", "enter.stake")]);
+        let profile = BehaviorProfile::new("test");
+        assert!(entering.decide_action(&wallet, &profile).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn highest_priority_wins() {
+        let mut engine = prioritized_engine(SelectionStrategy::HighestPriority);
+        let wallet = WalletState::new("test".into(), Address::ZERO);
+
+        assert_eq!(engine.plugins()[0].id(), "high");
+        for _ in 0..5 {
+            assert_eq!(chosen(&mut engine, &wallet).await, "high");
+        }
+    }
+
+    #[tokio::test]
+    async fn weighted_random_is_seeded() {
+        let wallet = WalletState::new("test".into(), Address::ZERO);
+        let mut runs = Vec::new();
+        for _ in 0..2 {
+            let mut engine = prioritized_engine(SelectionStrategy::WeightedRandom);
+            let mut picks = Vec::new();
+            for _ in 0..200 {
+                picks.push(chosen(&mut engine, &wallet).await);
+            }
+            runs.push(picks);
+        }
+
+        assert_eq!(runs[0], runs[1]);
+        let high = runs[0].iter().filter(|id| *id == "high").count();
+        assert!((120..=180).contains(&high), "high picked {high} times");
+    }
+
+    #[tokio::test]
+    async fn round_robin_takes_turns_per_wallet() {
+        let mut engine = prioritized_engine(SelectionStrategy::RoundRobin);
+        let first = WalletState::new("first".into(), Address::ZERO);
+        let second = WalletState::new("second".into(), Address::ZERO);
+
+        assert_eq!(chosen(&mut engine, &first).await, "high");
+        assert_eq!(chosen(&mut engine, &first).await, "low");
+        assert_eq!(chosen(&mut engine, &second).await, "high");
+        assert_eq!(chosen(&mut engine, &first).await, "high");
+    }
+
+    #[tokio::test]
+    async fn failing_plugin_does_not_block_others() {
+        let mut registry = PluginRegistry::new();
+        let broken = EagerPlugin {
+            fails: AtomicBool::new(true),
+            ..EagerPlugin::new("broken", "broken.act")
+        };
+        registry.register_with_priority(Arc::new(broken), 300).unwrap();
+        registry.register(Arc::new(EagerPlugin::new("ok", "ok.act"))).unwrap();
+        let ids = ["broken".to_string(), "ok".to_string()];
+        let mut engine = BehaviorEngine::new(&registry, &ids, SelectionStrategy::HighestPriority);
+        let wallet = WalletState::new("test".into(), Address::ZERO);
+
+        assert_eq!(chosen(&mut engine, &wallet).await, "ok");
+    }
+
+    /// Engine over plugin "flaky" (priority 300) and "steady" (priority
+    /// 100), disabling plugins with more than half of at least 3 decisions
+    /// or executions failing.
+    fn health_engine(
+        flaky: &Arc<EagerPlugin>,
+        clock: &Arc<fleet_core::clock::VirtualClock>,
+    ) -> BehaviorEngine {
+        let mut registry = PluginRegistry::new();
+        registry.register_with_priority(Arc::clone(flaky) as Arc<dyn ActionPlugin>, 300).unwrap();
+        registry.register(Arc::new(EagerPlugin::new("steady", "steady.act"))).unwrap();
+        let ids = ["flaky".to_string(), "steady".to_string()];
+        BehaviorEngine::new(&registry, &ids, SelectionStrategy::HighestPriority)
+            .with_clock(Arc::clone(clock) as _)
+            .with_plugin_health(HealthSettings {
+                min_calls: 3,
+                ..HealthSettings::default()
+            })
+    }
+
+    /// Decide for each of `wallets`, executing what was chosen; returns the
+    /// plugins chosen.
+    async fn serve(engine: &mut BehaviorEngine, wallets: &[WalletState]) -> Vec<String> {
+        let profile = BehaviorProfile::new("test");
+        let chain = evm_provider::mock::MockProvider::new();
+        let mut chosen = Vec::new();
+        for wallet in wallets {
+            let (plugin, action) = engine.decide_action(wallet, &profile).await.unwrap();
+            engine
+                .execute_action(plugin.as_ref(), &action, wallet, &chain)
+                .await
+                .unwrap();
+            chosen.push(plugin.id().to_string());
+        }
+        chosen
+    }
+
+    #[tokio::test]
+    async fn failing_plugin_is_disabled_and_probed() {
+        let clock = Arc::new(fleet_core::clock::VirtualClock::new(Utc::now()));
+        let flaky = Arc::new(EagerPlugin::new("flaky", "flaky.act"));
+        let mut engine = health_engine(&flaky, &clock);
+        let wallets: Vec<_> = (0..6)
+            .map(|i| WalletState::new(format!("w{i}"), Address::ZERO))
+            .collect();
+
+        assert_eq!(serve(&mut engine, &wallets[..2]).await, ["flaky", "flaky"]);
+
+        // Starts failing: disabled after 3 of 5 decisions failed, and the
+        // other plugin keeps serving every wallet
+        flaky.fails.store(true, Ordering::Relaxed);
+        assert_eq!(serve(&mut engine, &wallets).await, ["steady"; 6]);
+        assert_eq!(flaky.decided.load(Ordering::Relaxed), 5);
+        let health = engine.plugin_health();
+        assert_eq!(health[0].state, PluginState::Disabled);
+        assert_eq!(health[0].disables, 1);
+        assert_eq!(health[1].state, PluginState::Enabled);
+
+        // A probe asks a single wallet, and fails
+        clock.advance(chrono::Duration::minutes(5));
+        assert_eq!(serve(&mut engine, &wallets).await, ["steady"; 6]);
+        assert_eq!(flaky.decided.load(Ordering::Relaxed), 6);
+        assert_eq!(engine.plugin_health()[0].state, PluginState::Disabled);
+
+        // The next one finds it working again
+        flaky.fails.store(false, Ordering::Relaxed);
+        clock.advance(chrono::Duration::minutes(5));
+        assert_eq!(serve(&mut engine, &wallets).await, ["flaky"; 6]);
+        assert_eq!(engine.plugin_health()[0].state, PluginState::Enabled);
+        assert_eq!(engine.plugin_disabled_until("flaky"), None);
+    }
+
+    #[tokio::test]
+    async fn manual_override_beats_automatic_state() {
+        let clock = Arc::new(fleet_core::clock::VirtualClock::new(Utc::now()));
+        let flaky = Arc::new(EagerPlugin::new("flaky", "flaky.act"));
+        let mut engine = health_engine(&flaky, &clock);
+        let wallets: Vec<_> = (0..4)
+            .map(|i| WalletState::new(format!("w{i}"), Address::ZERO))
+            .collect();
+
+        // Disabled by hand while healthy, and never probed
+        assert!(engine.set_plugin_override("flaky", Some(PluginOverride::Disabled)));
+        clock.advance(chrono::Duration::hours(1));
+        assert_eq!(serve(&mut engine, &wallets).await, ["steady"; 4]);
+        assert_eq!(flaky.decided.load(Ordering::Relaxed), 0);
+        assert!(engine.plugin_disabled_until("flaky").is_some());
+
+        // Enabled by hand while failing: asked every time
+        flaky.fails.store(true, Ordering::Relaxed);
+        assert!(engine.set_plugin_override("flaky", Some(PluginOverride::Enabled)));
+        assert_eq!(serve(&mut engine, &wallets).await, ["steady"; 4]);
+        assert_eq!(flaky.decided.load(Ordering::Relaxed), 4);
+        let health = engine.plugin_health();
+        assert_eq!(health[0].state, PluginState::Enabled);
+        assert_eq!(health[0].manual, Some(PluginOverride::Enabled));
+
+        // Back to automatic: the failures count
+        assert!(engine.set_plugin_override("flaky", None));
+        assert_eq!(serve(&mut engine, &wallets[..1]).await, ["steady"]);
+        assert_eq!(engine.plugin_health()[0].state, PluginState::Disabled);
+        assert!(!engine.set_plugin_override("unknown", None));
+    }
+
+    /// Plugin whose executions fail with `class` until `failures` run out.
+    #[derive(Debug)]
+    struct FlakyPlugin {
+        class: ErrorClass,
+        failures: AtomicU32,
+        attempts: AtomicU32,
+    }
+
+    impl FlakyPlugin {
+        fn new(class: ErrorClass, failures: u32) -> Self {
+            Self {
+                class,
+                failures: AtomicU32::new(failures),
+                attempts: AtomicU32::new(0),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl ActionPlugin for FlakyPlugin {
+        fn id(&self) -> &'static str {
+            "flaky"
+        }
+
+        fn name(&self) -> &'static str {
+            "Flaky"
+        }
+
+        fn available_actions(&self) -> Vec<ActionId> {
+            vec![ActionId::new("flaky.act")]
+        }
+
+        async fn decide_action(
+            &self,
+            _wallet: &WalletState,
+            _profile: &BehaviorProfile,
+            _context: &mut PluginContext<'_>,
+        ) -> fleet_core::Result<Option<Action>> {
+            Ok(None)
+        }
+
+        async fn execute_action(
+            &self,
+            _action: &Action,
+            _wallet: &WalletState,
+            _nonce: u64,
+        ) -> fleet_core::Result<ActionResult> {
+            self.attempts.fetch_add(1, Ordering::SeqCst);
+            let failing = self
+                .failures
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                .is_ok();
+            if failing {
+                return Err(FleetError::plugin(self.class, "flaky"));
+            }
+            Ok(ActionResult::success(alloy::primitives::TxHash::ZERO))
+        }
+
+        async fn read_state(&self, _address: Address) -> fleet_core::Result<serde_json::Value> {
+            Ok(serde_json::Value::Null)
+        }
+    }
+
+    async fn execute(plugin: &FlakyPlugin, retries: u32) -> fleet_core::Result<ActionResult> {
+        let mut engine = engine(&[]).with_retry_policy(RetryPolicy {
+            retries,
+            delay: Duration::ZERO,
+        });
+        let wallet = WalletState::new("test".into(), Address::ZERO);
+        let chain = evm_provider::mock::MockProvider::new();
+        engine
+            .execute_action(plugin, &Action::new("flaky.act", "Act"), &wallet, &chain)
+            .await
+    }
+
+    #[tokio::test]
+    async fn retries_transient_errors_in_place() {
+        let plugin = FlakyPlugin::new(ErrorClass::Transient, 2);
+
+        let result = execute(&plugin, 2).await.unwrap();
+
+        assert!(result.is_success());
+        assert_eq!(plugin.attempts.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn returns_transient_error_once_retries_run_out() {
+        let plugin = FlakyPlugin::new(ErrorClass::Transient, 5);
+
+        let error = execute(&plugin, 2).await.unwrap_err();
+
+        assert_eq!(error.class(), ErrorClass::Transient);
+        assert_eq!(plugin.attempts.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn does_not_retry_other_classes() {
+        for class in [
+            ErrorClass::RateLimited,
+            ErrorClass::Permanent,
+            ErrorClass::Configuration,
+        ] {
+            let plugin = FlakyPlugin::new(class, 1);
+
+            let error = execute(&plugin, 2).await.unwrap_err();
+
+            assert_eq!(error.class(), class);
+            assert_eq!(plugin.attempts.load(Ordering::SeqCst), 1, "{class} was retried");
+        }
+    }
+
+    // ───────────────────────────────────────────────────────────────────────────
+    // Timeouts
+    // ───────────────────────────────────────────────────────────────────────────
+
+    /// Plugin that never finishes deciding or executing; executions send
+    /// their transaction first if it has a `chain` to send to.
+    #[derive(Debug, Default)]
+    struct HangingPlugin {
+        chain: Option<Arc<evm_provider::mock::MockProvider>>,
+        attempts: AtomicU32,
+    }
+
+    #[async_trait]
+    impl ActionPlugin for HangingPlugin {
+        fn id(&self) -> &'static str {
+            "hanging"
+        }
+
+        fn name(&self) -> &'static str {
+            "Hanging"
+        }
+
+        fn available_actions(&self) -> Vec<ActionId> {
+            vec![ActionId::new("hanging.act")]
+        }
+
+        async fn decide_action(
+            &self,
+            _wallet: &WalletState,
+            _profile: &BehaviorProfile,
+            _context: &mut PluginContext<'_>,
+        ) -> fleet_core::Result<Option<Action>> {
+            std::future::pending().await
+        }
+
+        async fn execute_action(
+            &self,
+            _action: &Action,
+            wallet: &WalletState,
+            nonce: u64,
+        ) -> fleet_core::Result<ActionResult> {
+            self.attempts.fetch_add(1, Ordering::SeqCst);
+            if let Some(chain) = &self.chain {
+                chain.set_nonce(wallet.address, nonce + 1);
+            }
+            std::future::pending().await
+        }
+
+        async fn read_state(&self, _address: Address) -> fleet_core::Result<serde_json::Value> {
+            Ok(serde_json::Value::Null)
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn cancels_decisions_that_take_too_long() {
+        let mut registry = PluginRegistry::new();
+        registry.register_with_priority(Arc::new(HangingPlugin::default()), 300).unwrap();
+        registry.register(Arc::new(EagerPlugin::new("ok", "ok.act"))).unwrap();
+        let ids = ["hanging".to_string(), "ok".to_string()];
+        let metrics = Arc::new(FleetMetrics::new());
+        let mut engine = BehaviorEngine::new(&registry, &ids, SelectionStrategy::HighestPriority)
+            .with_metrics(Arc::clone(&metrics));
+        let wallet = WalletState::new("test".into(), Address::ZERO);
+
+        assert_eq!(chosen(&mut engine, &wallet).await, "ok");
+        assert_eq!(metrics.timeouts_for_plugin("hanging"), 1);
+
+        // Forced, it still gets an action to run
+        let forced = ActionId::new("hanging.act");
+        let (plugin, action) = engine
+            .decide_forced(&wallet, &BehaviorProfile::new("test"), &forced)
+            .await
+            .unwrap();
+        assert_eq!((plugin.id(), action.id), ("hanging", forced));
+        assert_eq!(metrics.timeouts_for_plugin("hanging"), 2);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn cancels_executions_that_take_too_long() {
+        let metrics = Arc::new(FleetMetrics::new());
+        let mut engine = engine(&[])
+            .with_retry_policy(RetryPolicy {
+                retries: 1,
+                delay: Duration::ZERO,
+            })
+            .with_metrics(Arc::clone(&metrics));
+        let plugin = HangingPlugin::default();
+        let wallet = WalletState::new("test".into(), Address::ZERO);
+        let chain = evm_provider::mock::MockProvider::new();
+        let action = Action::new("hanging.act", "Act");
+
+        let error = engine
+            .execute_action(&plugin, &action, &wallet, &chain)
+            .await
+            .unwrap_err();
+
+        // Nothing was sent, so it was retried like any transient error
+        assert!(matches!(error, FleetError::PluginTimeout { call: "execute", .. }));
+        assert_eq!(error.class(), ErrorClass::Transient);
+        assert!(error.counts_toward_circuit_breaker());
+        assert_eq!(plugin.attempts.load(Ordering::SeqCst), 2);
+        assert_eq!(metrics.timeouts_for_plugin("hanging"), 2);
+        assert_eq!(engine.take_nonce_in_doubt("test"), None);
+
+        // The wallet is free to act again
+        let flaky = FlakyPlugin::new(ErrorClass::Transient, 0);
+        let result = engine
+            .execute_action(&flaky, &Action::new("flaky.act", "Act"), &wallet, &chain)
+            .await
+            .unwrap();
+        assert!(result.is_success());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn holds_nonce_of_execution_cancelled_after_sending() {
+        let chain = Arc::new(evm_provider::mock::MockProvider::new());
+        let plugin = HangingPlugin {
+            chain: Some(Arc::clone(&chain)),
+            ..HangingPlugin::default()
+        };
+        let mut engine = engine(&[]).with_action_timeouts(ActionTimeouts {
+            plugin_execute: HashMap::from([("hanging".to_string(), Duration::from_secs(5))]),
+            ..ActionTimeouts::default()
+        });
+        let mut wallet = WalletState::new("test".into(), Address::ZERO);
+        wallet.set_nonce(NONCE);
+        chain.set_nonce(wallet.address, NONCE);
+
+        let started = tokio::time::Instant::now();
+        let error = engine
+            .execute_action(&plugin, &Action::new("hanging.act", "Act"), &wallet, &*chain)
+            .await
+            .unwrap_err();
+
+        // Not retried with the nonce it may have used
+        assert_eq!(error.class(), ErrorClass::Transient);
+        assert!((5..120).contains(&started.elapsed().as_secs()));
+        assert_eq!(plugin.attempts.load(Ordering::SeqCst), 1);
+        assert_eq!(engine.take_nonce_in_doubt("test"), Some(NONCE));
+        assert_eq!(engine.take_nonce_in_doubt("test"), None);
+    }
+
+    #[test]
+    fn plugin_timeouts_override_defaults() {
+        let timeouts = ActionTimeouts {
+            plugin_decide: HashMap::from([("slow".to_string(), Duration::from_secs(30))]),
+            ..ActionTimeouts::default()
+        };
+
+        assert_eq!(timeouts.decide_timeout("slow"), Duration::from_secs(30));
+        assert_eq!(timeouts.decide_timeout("other"), Duration::from_secs(10));
+        assert_eq!(timeouts.execute_timeout("slow"), Duration::from_secs(120));
+    }
+
+    // ───────────────────────────────────────────────────────────────────────────
+    // Stuck transactions
+    // ───────────────────────────────────────────────────────────────────────────
+
+    /// Hash of the original transaction, which the chain never mines.
+    const ORIGINAL: TxHash = TxHash::repeat_byte(0xaa);
+
+    /// Nonce the original was sent with.
+    const NONCE: u64 = 7;
+
+    /// Chain that mines the sent transactions, or only those marked mined.
+    #[derive(Debug, Default)]
+    struct StuckChain {
+        mines_sent: bool,
+        mined: std::sync::Mutex<std::collections::HashSet<TxHash>>,
+        sent: std::sync::Mutex<Vec<Bytes>>,
+    }
+
+    impl StuckChain {
+        fn sent(&self) -> Vec<TxEnvelope> {
+            use alloy::eips::Decodable2718;
+            let sent = self.sent.lock().unwrap();
+            sent.iter()
+                .map(|raw| TxEnvelope::decode_2718(&mut raw.as_ref()).unwrap())
+                .collect()
+        }
+    }
+
+    #[async_trait]
+    impl ChainProvider for StuckChain {
+        fn chain_id(&self) -> u64 {
+            31337
+        }
+
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+
+        async fn get_balance(&self, _address: Address) -> evm_provider::Result<U256> {
+            Ok(U256::ZERO)
+        }
+
+        async fn get_nonce(&self, _address: Address) -> evm_provider::Result<u64> {
+            Ok(NONCE)
+        }
+
+        async fn send_raw_transaction(&self, tx: Bytes) -> evm_provider::Result<TxHash> {
+            let tx_hash = alloy::primitives::keccak256(&tx);
+            if self.mines_sent {
+                self.mined.lock().unwrap().insert(tx_hash);
+            }
+            self.sent.lock().unwrap().push(tx);
+            Ok(tx_hash)
+        }
+
+        async fn wait_for_receipt(
+            &self,
+            tx_hash: TxHash,
+            timeout: Duration,
+        ) -> evm_provider::Result<TransactionReceipt> {
+            if !self.mined.lock().unwrap().contains(&tx_hash) {
+                return Err(evm_provider::ProviderError::Timeout(timeout));
+            }
+            Ok(TransactionReceipt {
+                tx_hash,
+                block_hash: alloy::primitives::B256::ZERO,
+                block_number: 100,
+                tx_index: 0,
+                from: Address::ZERO,
+                to: None,
+                contract_address: None,
+                gas_used: 21_000,
+                success: true,
+                logs: vec![],
+            })
+        }
+
+        async fn gas_price(&self) -> evm_provider::Result<u128> {
+            Ok(1_000)
+        }
+
+        async fn get_block_number(&self) -> evm_provider::Result<u64> {
+            Ok(100)
+        }
+
+        async fn call(
+            &self,
+            _tx: &evm_provider::TransactionRequest,
+        ) -> evm_provider::Result<Bytes> {
+            Ok(Bytes::new())
+        }
+    }
+
+    /// Submits the standard way.
+    impl ExtendedChainProvider for StuckChain {}
+
+    /// Plugin whose transaction, sent at `gas_price` if given, is never
+    /// mined, and that replaces it if it has a signer.
+    #[derive(Debug, Default)]
+    struct StuckPlugin {
+        signer: Option<PrivateKeySigner>,
+        gas_price: Option<u128>,
+        offered: std::sync::Mutex<Vec<(PendingTx, u32)>>,
+    }
+
+    #[async_trait]
+    impl ActionPlugin for StuckPlugin {
+        fn id(&self) -> &'static str {
+            "stuck"
+        }
+
+        fn name(&self) -> &'static str {
+            "Stuck"
+        }
+
+        fn available_actions(&self) -> Vec<ActionId> {
+            vec![ActionId::new("stuck.act")]
+        }
+
+        async fn decide_action(
+            &self,
+            _wallet: &WalletState,
+            _profile: &BehaviorProfile,
+            _context: &mut PluginContext<'_>,
+        ) -> fleet_core::Result<Option<Action>> {
+            Ok(None)
+        }
+
+        async fn execute_action(
+            &self,
+            _action: &Action,
+            _wallet: &WalletState,
+            _nonce: u64,
+        ) -> fleet_core::Result<ActionResult> {
+            let mut result = ActionResult::dropped(ORIGINAL, "receipt timed out")
+                .with_gas_estimate(150_000)
+                .with_detail(serde_json::json!({ "level": 3 }));
+            result.effective_gas_price = self.gas_price;
+            Ok(result)
+        }
+
+        async fn build_replacement(
+            &self,
+            _action: &Action,
+            original: &PendingTx,
+            bump_pct: u32,
+        ) -> fleet_core::Result<Option<Bytes>> {
+            self.offered.lock().unwrap().push((*original, bump_pct));
+            let Some(signer) = &self.signer else {
+                return Ok(None);
+            };
+            let tx = TxEip1559 {
+                chain_id: 31337,
+                nonce: original.nonce,
+                gas_limit: 200_000,
+                max_fee_per_gas: bump_fee(2_000, bump_pct),
+                to: TxKind::Call(Address::repeat_byte(0xc0)),
+                ..TxEip1559::default()
+            };
+            let signature = signer.sign_hash_sync(&tx.signature_hash()).unwrap();
+            Ok(Some(TxEnvelope::from(tx.into_signed(signature)).encoded_2718().into()))
+        }
+
+        async fn read_state(&self, _address: Address) -> fleet_core::Result<serde_json::Value> {
+            Ok(serde_json::Value::Null)
+        }
+
+        fn state_from_receipt(
+            &self,
+            _action: &Action,
+            _wallet: &WalletState,
+            receipt: &TransactionReceipt,
+        ) -> Option<serde_json::Value> {
+            Some(serde_json::json!({ "block": receipt.block_number }))
+        }
+
+        fn data_flows(
+            &self,
+            _action: &Action,
+            _wallet: &WalletState,
+            _receipt: &TransactionReceipt,
+        ) -> Vec<DataFlow> {
+            vec![DataFlow::Wagered {
+                amount: U256::from(5),
+            }]
+        }
+    }
+
+    async fn settle(plugin: &StuckPlugin, chain: &StuckChain) -> ActionResult {
+        let mut engine = engine(&[]).with_replacement_policy(ReplacementPolicy {
+            receipt_timeout: Duration::ZERO,
+            ..ReplacementPolicy::default()
+        });
+        let mut wallet = WalletState::new("test".into(), Address::ZERO);
+        wallet.set_nonce(NONCE);
+        let action = Action::new("stuck.act", "Act");
+
+        let result = engine.execute_action(plugin, &action, &wallet, chain).await.unwrap();
+        let signer = LocalSigner::new(PrivateKeySigner::random());
+        engine.settle(plugin, &action, &wallet, chain, &signer, result).await
+    }
+
+    #[test]
+    fn replacement_fees_compound() {
+        let policy = ReplacementPolicy {
+            fee_bump_pct: 10,
+            ..ReplacementPolicy::default()
+        };
+        // 110%, 121%, 133.1% rounded up
+        let bumps: Vec<_> = (1..=3).map(|attempt| policy.bump_for(attempt)).collect();
+        assert_eq!(bumps, [10, 21, 34]);
+        assert_eq!(policy.bump_for(0), 0);
+    }
+
+    #[tokio::test]
+    async fn replacement_with_the_same_nonce_is_mined() {
+        let plugin = StuckPlugin {
+            signer: Some(PrivateKeySigner::random()),
+            ..StuckPlugin::default()
+        };
+        let chain = StuckChain {
+            mines_sent: true,
+            ..StuckChain::default()
+        };
+
+        let result = settle(&plugin, &chain).await;
+
+        let sent = chain.sent();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(alloy::consensus::Transaction::nonce(&sent[0]), NONCE);
+        assert_eq!(result.status, ActionStatus::Succeeded);
+        assert_eq!(result.tx_hash, Some(*sent[0].tx_hash()));
+        assert_eq!(result.replacement.unwrap().outcome, ReplacementOutcome::ReplacementMined);
+        assert_eq!(result.detail["level"], 3);
+        assert_eq!(result.gas_estimate, Some(150_000));
+        assert_eq!(result.plugin_state, Some(serde_json::json!({ "block": 100 })));
+
+        let expected = PendingTx {
+            tx_hash: ORIGINAL,
+            nonce: NONCE,
+            replacements: 0,
+            gas_price: None,
+        };
+        assert_eq!(*plugin.offered.lock().unwrap(), [(expected, 12)]);
+    }
+
+    #[tokio::test]
+    async fn declined_replacement_is_cancelled() {
+        use alloy::consensus::Transaction as _;
+        let chain = StuckChain {
+            mines_sent: true,
+            ..StuckChain::default()
+        };
+
+        let result = settle(&StuckPlugin::default(), &chain).await;
+
+        let cancel = &chain.sent()[0];
+        assert_eq!(cancel.nonce(), NONCE);
+        assert_eq!(cancel.gas_limit(), TRANSFER_GAS);
+        assert_eq!(cancel.value(), U256::ZERO);
+        assert_eq!(cancel.max_fee_per_gas(), 1_120);
+        let sender = alloy::consensus::transaction::SignerRecoverable::recover_signer(cancel);
+        assert_eq!(cancel.to(), Some(sender.unwrap()));
+
+        // The action did not happen, but the cancel spent gas and the nonce
+        assert_eq!(result.status, ActionStatus::Dropped);
+        assert_eq!(result.gas_cost_wei(), Some(21_000 * 1_120));
+        assert!(result.plugin_state.is_none());
+        let replacement = result.replacement.unwrap();
+        assert_eq!((replacement.outcome, replacement.attempts), (ReplacementOutcome::Cancelled, 1));
+        assert!(replacement.outcome.used_nonce());
+    }
+
+    #[tokio::test]
+    async fn cancel_outbids_the_original_after_the_gas_price_falls() {
+        use alloy::consensus::Transaction as _;
+        let plugin = StuckPlugin {
+            gas_price: Some(2_000),
+            ..StuckPlugin::default()
+        };
+        let chain = StuckChain {
+            mines_sent: true,
+            ..StuckChain::default()
+        };
+
+        let result = settle(&plugin, &chain).await;
+
+        // 12% over the original's 2000 wei, not the current 1000
+        assert_eq!(chain.sent()[0].max_fee_per_gas(), 2_240);
+        assert_eq!(result.gas_cost_wei(), Some(21_000 * 2_240));
+        assert_eq!(plugin.offered.lock().unwrap()[0].0.gas_price, Some(2_000));
+    }
+
+    #[tokio::test]
+    async fn abandons_after_the_last_replacement() {
+        use alloy::consensus::Transaction as _;
+        let plugin = StuckPlugin::default();
+        let chain = StuckChain::default();
+
+        let result = settle(&plugin, &chain).await;
+
+        let sent = chain.sent();
+        assert_eq!(sent.len(), 3);
+        assert!(sent.iter().all(|tx| tx.nonce() == NONCE));
+        let fees: Vec<_> = sent
+            .iter()
+            .map(alloy::consensus::Transaction::max_fee_per_gas)
+            .collect();
+        assert_eq!(fees, [1_120, 1_260, 1_420]);
+
+        assert_eq!(result.tx_hash, Some(ORIGINAL));
+        assert_eq!(result.status, ActionStatus::Dropped);
+        let replacement = result.replacement.unwrap();
+        assert_eq!((replacement.outcome, replacement.attempts), (ReplacementOutcome::Abandoned, 3));
+        assert_eq!(plugin.offered.lock().unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn late_original_is_not_replaced() {
+        let chain = StuckChain::default();
+        chain.mined.lock().unwrap().insert(ORIGINAL);
+
+        let result = settle(&StuckPlugin::default(), &chain).await;
+
+        assert!(chain.sent().is_empty());
+        assert_eq!(result.status, ActionStatus::Succeeded);
+        assert_eq!(result.tx_hash, Some(ORIGINAL));
+        assert_eq!(result.replacement.unwrap().outcome, ReplacementOutcome::OriginalMined);
+        assert!(result.plugin_state.is_some());
+        assert_eq!(
+            result.data_flows,
+            [DataFlow::Wagered {
+                amount: U256::from(5)
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn sweeps_leave_gas_and_move_whole_tokens() {
+        use alloy::consensus::Transaction as _;
+        let engine = engine(&[]);
+        let chain = StuckChain {
+            mines_sent: true,
+            ..StuckChain::default()
+        };
+        let signer = LocalSigner::new(PrivateKeySigner::random());
+        let to = Address::repeat_byte(0x55);
+        let token = Address::repeat_byte(0xda);
+        let mut wallet = WalletState::new("test".into(), Address::ZERO);
+        wallet.set_native_balance(U256::from(1_000_000_000_u64));
+        wallet.set_token_balance(token, U256::from(42), 1);
+
+        let native = engine.sweep(&wallet, &chain, &signer, SweepAsset::Native, to).await;
+        let tokens = engine.sweep(&wallet, &chain, &signer, SweepAsset::Token(token), to).await;
+        assert_eq!(native.unwrap().status, ActionStatus::Succeeded);
+        assert_eq!(tokens.unwrap().effective_gas_price, Some(1_000));
+
+        // All but the transfer's gas at 1000 wei, and the whole token balance
+        let sent = chain.sent();
+        assert_eq!(sent[0].to(), Some(to));
+        assert_eq!(sent[0].value(), U256::from(1_000_000_000_u64 - 21_000 * 1_000));
+        assert_eq!((sent[1].to(), sent[1].value()), (Some(token), U256::ZERO));
+        let transfer = transferCall::abi_decode(sent[1].input()).unwrap();
+        assert_eq!((transfer.to, transfer.amount), (to, U256::from(42)));
+
+        // A balance that does not cover the gas is not swept
+        wallet.set_native_balance(U256::from(21_000 * 1_000));
+        let dust = engine.sweep(&wallet, &chain, &signer, SweepAsset::Native, to).await;
+        assert_eq!(dust.unwrap().status, ActionStatus::Skipped);
+        assert_eq!(chain.sent().len(), 2);
+    }
+
+    // ───────────────────────────────────────────────────────────────────────────
+    // Submission paths
+    // ───────────────────────────────────────────────────────────────────────────
+
+    /// Sweep a wallet's native balance on `chain`, whose realtime support is
+    /// set to `realtime`.
+    async fn sweep_on(
+        engine: &BehaviorEngine,
+        chain: &evm_provider::mock::MockProvider,
+        realtime: bool,
+    ) -> ActionResult {
+        chain.set_realtime_support(realtime);
+        let mut wallet = WalletState::new("test".into(), Address::ZERO);
+        wallet.set_native_balance(U256::from(1_000_000_000_000_000_u64));
+        let signer = LocalSigner::new(PrivateKeySigner::random());
+        let to = Address::repeat_byte(0x55);
+        engine
+            .sweep(&wallet, chain, &signer, SweepAsset::Native, to)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn realtime_capable_chains_answer_with_the_receipt() {
+        use evm_provider::fault::{FaultPlan, Method};
+        let chain = evm_provider::mock::MockProvider::new();
+        let plan = Arc::new(FaultPlan::new(0));
+        chain.set_fault_plan(Arc::clone(&plan));
+
+        let result = sweep_on(&engine(&[]), &chain, true).await;
+
+        assert_eq!(result.status, ActionStatus::Succeeded);
+        let submission = result.submission.unwrap();
+        assert_eq!(submission.path, SubmissionPath::Realtime);
+        assert!(submission.receipt_latency_ms.is_some());
+        assert_eq!(plan.calls(Method::SendRealtime), 1);
+        assert_eq!(plan.calls(Method::SendRawTransaction), 0);
+        assert_eq!(plan.calls(Method::WaitForReceipt), 0);
+    }
+
+    #[tokio::test]
+    async fn other_chains_are_polled_for_the_receipt() {
+        use evm_provider::fault::{FaultPlan, Method};
+        let chain = evm_provider::mock::MockProvider::new();
+        let plan = Arc::new(FaultPlan::new(0));
+        chain.set_fault_plan(Arc::clone(&plan));
+
+        let result = sweep_on(&engine(&[]), &chain, false).await;
+        let forced = engine(&[]).with_force_standard_submission(true);
+        let forced = sweep_on(&forced, &chain, true).await;
+        let mut down = engine(&[]);
+        down.set_realtime_down(true);
+        let down = sweep_on(&down, &chain, true).await;
+
+        for result in [result, forced, down] {
+            assert_eq!(result.status, ActionStatus::Succeeded);
+            assert_eq!(result.submission.unwrap().path, SubmissionPath::Standard);
+        }
+        assert_eq!(plan.calls(Method::SendRealtime), 0);
+        assert_eq!(plan.calls(Method::SendRawTransaction), 3);
+        assert_eq!(plan.calls(Method::WaitForReceipt), 3);
+    }
+
+    #[tokio::test]
+    async fn failed_realtime_submissions_fall_back_per_action() {
+        use evm_provider::fault::{Fault, FaultPlan, Method};
+        let chain = evm_provider::mock::MockProvider::new();
+        let plan = Arc::new(FaultPlan::new(0).fail_next(Method::SendRealtime, 1, Fault::Timeout));
+        chain.set_fault_plan(Arc::clone(&plan));
+        let engine = engine(&[]);
+
+        let fallen_back = sweep_on(&engine, &chain, true).await;
+        let next = sweep_on(&engine, &chain, true).await;
+
+        assert_eq!(fallen_back.status, ActionStatus::Succeeded);
+        assert_eq!(fallen_back.submission.unwrap().path, SubmissionPath::Standard);
+        assert_eq!(next.submission.unwrap().path, SubmissionPath::Realtime);
+        assert_eq!(plan.calls(Method::SendRealtime), 2);
+        assert_eq!(plan.calls(Method::SendRawTransaction), 1);
+    }
+
+    #[tokio::test]
+    async fn other_results_are_left_alone() {
+        let engine = engine(&[]);
+        let wallet = WalletState::new("test".into(), Address::ZERO);
+        let action = Action::new("stuck.act", "Act");
+        let chain = StuckChain::default();
+        let signer = LocalSigner::new(PrivateKeySigner::random());
+
+        for result in [
+            ActionResult::success(ORIGINAL),
+            ActionResult::reverted(ORIGINAL, "out of gas"),
+            ActionResult::failure("not sent"),
+        ] {
+            let settled = engine
+                .settle(&StuckPlugin::default(), &action, &wallet, &chain, &signer, result.clone())
+                .await;
+            assert_eq!(settled, result);
+        }
+        assert!(chain.sent().is_empty());
+    }
 }
@@ -2,6 +2,8 @@
 
 use std::path::PathBuf;
 
+use alloy::primitives::Address;
+use fleet_core::validation::ConfigReport;
 use thiserror::Error;
 
 /// Result type for Ghost Fleet operations.
@@ -23,6 +25,10 @@ pub enum FleetServiceError {
     #[error("Fleet error: {0}")]
     Fleet(#[from] fleet_core::FleetError),
 
+    /// Signing key error.
+    #[error("Signer error: {0}")]
+    Signer(#[from] SignerError),
+
     /// Plugin error.
     #[error("Plugin error: {0}")]
     Plugin(String),
@@ -35,6 +41,26 @@ pub enum FleetServiceError {
     #[error("No signer for wallet: {0}")]
     NoSigner(String),
 
+    /// Control request rejected or failed.
+    #[error("Control error: {0}")]
+    Control(String),
+
+    /// Activity report could not be written or posted.
+    #[error("Report error: {0}")]
+    Report(String),
+
+    /// Saved fleet state could not be read or written.
+    #[error("State error: {0}")]
+    State(String),
+
+    /// Fleet lease could not be read or written.
+    #[error("Lease error: {0}")]
+    Lease(String),
+
+    /// Source of protocol events failed.
+    #[error("Event stream error: {0}")]
+    Trigger(String),
+
     /// Internal error.
     #[error("Internal error: {0}")]
     Internal(String),
@@ -61,7 +87,99 @@ pub enum ConfigError {
         source: toml::de::Error,
     },
 
+    /// Failed to parse a deployment manifest.
+    #[error("Failed to parse deployment manifest {path}: {source}")]
+    Manifest {
+        /// Path to the file.
+        path: PathBuf,
+        /// JSON parse error.
+        source: serde_json::Error,
+    },
+
     /// Validation error.
     #[error("Config validation failed: {0}")]
     Validation(String),
+
+    /// Validation found errors in the config.
+    #[error("Config validation failed:\n{0}")]
+    Invalid(ConfigReport),
+
+    /// The RPC endpoint serves another chain than configured.
+    #[error("{chain}.chain_id is {expected} but the RPC endpoint reports chain {actual}")]
+    ChainIdMismatch {
+        /// Config key of the chain.
+        chain: String,
+        /// Configured chain ID.
+        expected: u64,
+        /// Chain ID reported by the provider.
+        actual: u64,
+    },
+}
+
+/// Errors loading wallet signing keys.
+///
+/// Every variant names the wallet whose key failed to load.
+#[derive(Debug, Error)]
+pub enum SignerError {
+    /// Failed to read a password or seed phrase.
+    #[error("Wallet {wallet}: failed to read {secret}: {source}")]
+    Secret {
+        /// Wallet ID.
+        wallet: String,
+        /// What was being read.
+        secret: String,
+        /// IO error.
+        source: std::io::Error,
+    },
+
+    /// Raw private key is not a valid hex secp256k1 key.
+    #[error("Wallet {wallet}: invalid private key: {reason}")]
+    InvalidKey {
+        /// Wallet ID.
+        wallet: String,
+        /// Why the key is invalid.
+        reason: String,
+    },
+
+    /// Failed to read or decrypt a keystore file.
+    #[error("Wallet {wallet}: failed to decrypt keystore {path}: {reason}")]
+    Keystore {
+        /// Wallet ID.
+        wallet: String,
+        /// Path to the keystore file.
+        path: PathBuf,
+        /// Why decryption failed.
+        reason: String,
+    },
+
+    /// Failed to derive a key from a mnemonic.
+    #[error("Wallet {wallet}: failed to derive key from mnemonic '{mnemonic}': {reason}")]
+    Derivation {
+        /// Wallet ID.
+        wallet: String,
+        /// Mnemonic name.
+        mnemonic: String,
+        /// Why derivation failed.
+        reason: String,
+    },
+
+    /// A remote signer could not be set up.
+    #[error("Wallet {wallet}: invalid remote signer: {reason}")]
+    Remote {
+        /// Wallet ID.
+        wallet: String,
+        /// Why the signer could not be set up.
+        reason: String,
+    },
+
+    /// The key does not belong to the wallet's configured address.
+    #[error("Wallet {wallet}: key is for {actual}, but the configured address is {expected}")]
+    AddressMismatch {
+        /// Wallet ID.
+        wallet: String,
+        /// Configured address.
+        expected: Address,
+        /// Address of the loaded key.
+        actual: Address,
+    },
 }
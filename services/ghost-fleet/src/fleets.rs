@@ -0,0 +1,393 @@
+//! Several named fleets in one process.
+//!
+//! Each `[fleet.<name>]` runs as a [`FleetService`] of its own, with its own
+//! wallets, scheduler, circuit breakers, budgets and rate limits: a fleet
+//! whose wallets keep failing, or that spent its budget, never holds up
+//! another. The fleets share the RPC endpoint pool, so endpoint health and
+//! the spreading of requests over endpoints are process-wide, and one main
+//! loop ticks them one after another.
+//!
+//! Wallet ids only need to be unique within a fleet. Logs of a fleet's tick
+//! are in a `fleet` span, its snapshots and review journal name it, and the
+//! roll-up of [`Fleets::snapshot`] keys wallets `<fleet>/<wallet>`.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use evm_provider::ChainProvider;
+use fleet_core::clock::system_clock;
+use fleet_core::metrics::FleetSnapshot;
+use tokio::sync::watch;
+use tokio::time::interval;
+use tracing::{Instrument, info, info_span};
+
+use crate::config::Settings;
+use crate::service::{FleetService, Runtime};
+use crate::signer::Keyring;
+
+/// The fleets of `[fleet.<name>]`, run side by side.
+#[derive(Debug)]
+pub struct Fleets {
+    /// Services of the fleets, in name order.
+    services: Vec<FleetService>,
+
+    /// Time between ticks of the main loop.
+    tick_interval: Duration,
+
+    /// Time between saves of the fleets' state.
+    save_interval: Duration,
+}
+
+impl Fleets {
+    /// Create a service for each fleet of `settings` on one endpoint pool.
+    ///
+    /// `signers` loads the signing keys of one fleet's settings.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the pool cannot be created, serves another chain,
+    /// or the keys of a fleet fail to load.
+    pub fn new(
+        settings: &Settings,
+        dry_run: bool,
+        mut signers: impl FnMut(&Settings) -> Result<Keyring>,
+    ) -> Result<Self> {
+        let pool = FleetService::create_pool(settings)?;
+        settings.check_chain_id(pool.primary().chain_id())?;
+        let clock = system_clock();
+
+        let fleets = settings
+            .fleet_settings()
+            .into_iter()
+            .map(|(name, settings)| {
+                let signers = signers(&settings)
+                    .with_context(|| format!("Failed to load wallet keys of fleet '{name}'"))?;
+                let registry =
+                    FleetService::create_registry(&settings, Arc::clone(pool.primary().inner()))?;
+                let runtime = Runtime {
+                    pool: Arc::clone(&pool),
+                    registry,
+                    clock: Arc::clone(&clock),
+                    seed: None,
+                    signers,
+                };
+                let span = info_span!("fleet", fleet = %name);
+                let mut service = span
+                    .in_scope(|| FleetService::with_runtime(settings, dry_run, runtime))
+                    .with_fleet_id(name.clone());
+                span.in_scope(|| service.load_state())
+                    .with_context(|| format!("Failed to load the state of fleet '{name}'"))?;
+                Ok(service)
+            })
+            .collect::<Result<_>>()?;
+
+        let tick_interval = Duration::from_millis(settings.service.tick_interval_ms);
+        let save_interval = Duration::from_secs(settings.service.state_save_interval_secs);
+        Ok(Self::from_services(fleets, tick_interval).with_save_interval(save_interval))
+    }
+
+    /// Run `fleets`, ticking them every `tick_interval` and saving their
+    /// state every minute.
+    #[must_use]
+    pub const fn from_services(fleets: Vec<FleetService>, tick_interval: Duration) -> Self {
+        Self {
+            services: fleets,
+            tick_interval,
+            save_interval: Duration::from_secs(60),
+        }
+    }
+
+    /// Save the fleets' state every `save_interval` instead.
+    #[must_use]
+    pub const fn with_save_interval(mut self, save_interval: Duration) -> Self {
+        self.save_interval = save_interval;
+        self
+    }
+
+    /// Run the fleets until the shutdown signal is received.
+    ///
+    /// Each fleet's state is saved every `save_interval` and on shutdown,
+    /// see [`FleetService::save_state`].
+    ///
+    /// # Errors
+    ///
+    /// Does not currently fail; the result mirrors [`FleetService::run`].
+    pub async fn run(mut self, mut shutdown: watch::Receiver<bool>) -> Result<()> {
+        let mut tick = interval(self.tick_interval);
+        let mut save = interval(self.save_interval.max(Duration::from_secs(1)));
+
+        info!(
+            fleets = self.services.len(),
+            tick_ms = self.tick_interval.as_millis(),
+            "Starting main loop"
+        );
+
+        loop {
+            tokio::select! {
+                _ = tick.tick() => {
+                    self.process_tick().await;
+                }
+                _ = save.tick() => {
+                    self.save_state();
+                }
+                _ = shutdown.changed() => {
+                    if *shutdown.borrow() {
+                        self.save_state();
+                        let total = self.snapshot();
+                        info!(
+                            actions = total.total_actions,
+                            succeeded = total.successful_actions,
+                            failed = total.failed_actions,
+                            "Shutdown signal received, stopping fleets"
+                        );
+                        return Ok(());
+                    }
+                }
+            }
+        }
+    }
+
+    /// Process one tick of every fleet, in name order.
+    pub async fn process_tick(&mut self) {
+        for fleet in &mut self.services {
+            let span = info_span!("fleet", fleet = fleet.fleet_id().unwrap_or_default());
+            fleet.process_tick().instrument(span).await;
+        }
+    }
+
+    /// Save the state of every fleet, see [`FleetService::save_state`].
+    pub fn save_state(&self) {
+        for fleet in &self.services {
+            let span = info_span!("fleet", fleet = fleet.fleet_id().unwrap_or_default());
+            span.in_scope(|| fleet.save_state());
+        }
+    }
+
+    /// Snapshot of each fleet, in name order.
+    #[must_use]
+    pub fn snapshots(&self) -> Vec<FleetSnapshot> {
+        self.services.iter().map(FleetService::snapshot).collect()
+    }
+
+    /// Roll-up of the snapshots of all fleets.
+    #[must_use]
+    pub fn snapshot(&self) -> FleetSnapshot {
+        FleetSnapshot::roll_up(&self.snapshots())
+    }
+
+    /// The fleet named `id`.
+    #[must_use]
+    #[allow(dead_code)] // Used in tests
+    pub fn fleet(&self, id: &str) -> Option<&FleetService> {
+        self.services.iter().find(|fleet| fleet.fleet_id() == Some(id))
+    }
+
+    /// Earliest wake-up of any fleet, see [`FleetService::next_wakeup`].
+    #[must_use]
+    #[allow(dead_code)] // Used in tests
+    pub fn next_wakeup(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.services.iter().filter_map(FleetService::next_wakeup).min()
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use alloy::primitives::{Address, TxHash};
+    use async_trait::async_trait;
+    use chrono::{TimeZone, Utc};
+    use evm_provider::mock::MockProvider;
+    use evm_provider::pool::ProviderPool;
+    use fleet_core::clock::{Clock, VirtualClock};
+    use fleet_core::plugins::{
+        Action, ActionId, ActionPlugin, ActionResult, PluginContext, PluginRegistry,
+    };
+    use fleet_core::profiles::BehaviorProfile;
+    use fleet_core::wallet::WalletState;
+    use fleet_core::{ErrorClass, FleetError};
+
+    use super::*;
+
+    /// Two fleets of the same two wallet ids, which may act three times a
+    /// day and trip their breaker after two errors in a row.
+    const CONFIG: &str = r#"
+        [chain]
+        chain_id = 31337
+        rpc_url = "http://localhost:8545"
+        chain_type = "mock"
+
+        [plugins]
+        enabled = ["stub"]
+
+        [safety]
+        max_consecutive_errors = 2
+        cooldown_secs = 86400
+
+        [profiles.steady]
+        action_interval_secs = 600
+        afk_probability = 0.0
+        burst_probability = 0.0
+        budget = { daily_actions = 3 }
+
+        [[fleet.alpha.wallets]]
+        id = "w1"
+        address = "0x0000000000000000000000000000000000000001"
+        profile = "steady"
+
+        [[fleet.alpha.wallets]]
+        id = "w2"
+        address = "0x0000000000000000000000000000000000000002"
+        profile = "steady"
+
+        [[fleet.beta.wallets]]
+        id = "w1"
+        address = "0x0000000000000000000000000000000000000003"
+        profile = "steady"
+
+        [[fleet.beta.wallets]]
+        id = "w2"
+        address = "0x0000000000000000000000000000000000000004"
+        profile = "steady"
+    "#;
+
+    /// Plugin that always decides `stub.act`, and fails it if `failing`.
+    #[derive(Debug)]
+    struct StubPlugin {
+        failing: bool,
+        executed: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl ActionPlugin for StubPlugin {
+        fn id(&self) -> &'static str {
+            "stub"
+        }
+
+        fn name(&self) -> &'static str {
+            "Stub"
+        }
+
+        fn available_actions(&self) -> Vec<ActionId> {
+            vec![ActionId::new("stub.act")]
+        }
+
+        async fn decide_action(
+            &self,
+            _wallet: &WalletState,
+            _profile: &BehaviorProfile,
+            _context: &mut PluginContext<'_>,
+        ) -> fleet_core::Result<Option<Action>> {
+            Ok(Some(Action::new("stub.act", "Act")))
+        }
+
+        async fn execute_action(
+            &self,
+            _action: &Action,
+            _wallet: &WalletState,
+            _nonce: u64,
+        ) -> fleet_core::Result<ActionResult> {
+            self.executed.fetch_add(1, Ordering::SeqCst);
+            if self.failing {
+                return Err(FleetError::plugin(ErrorClass::Permanent, "broken"));
+            }
+            Ok(ActionResult::success(TxHash::ZERO))
+        }
+
+        async fn read_state(&self, _address: Address) -> fleet_core::Result<serde_json::Value> {
+            Ok(serde_json::Value::Null)
+        }
+    }
+
+    /// The fleets of [`CONFIG`] on one pool and `clock`; `alpha`'s actions
+    /// fail, `beta`'s succeed.
+    fn fleets(clock: &Arc<VirtualClock>) -> (Fleets, Vec<Arc<StubPlugin>>) {
+        let settings: Settings = toml::from_str(CONFIG).unwrap();
+        settings.validate().unwrap();
+        let pool = Arc::new(ProviderPool::single(
+            "primary",
+            Arc::new(MockProvider::with_chain_id(31337)),
+        ));
+
+        let mut plugins = Vec::new();
+        let services = settings
+            .fleet_settings()
+            .into_iter()
+            .map(|(name, settings)| {
+                let plugin = Arc::new(StubPlugin {
+                    failing: name == "alpha",
+                    executed: AtomicUsize::new(0),
+                });
+                let mut registry = PluginRegistry::new();
+                registry.register(Arc::clone(&plugin) as Arc<dyn ActionPlugin>).unwrap();
+                plugins.push(plugin);
+                let ids = settings.wallets.iter().map(|w| w.id.as_str());
+                let runtime = Runtime {
+                    pool: Arc::clone(&pool),
+                    registry,
+                    clock: Arc::clone(clock) as _,
+                    seed: Some(7),
+                    signers: Keyring::development(ids).unwrap(),
+                };
+                FleetService::with_runtime(settings, false, runtime).with_fleet_id(name)
+            })
+            .collect();
+        (Fleets::from_services(services, Duration::from_millis(10)), plugins)
+    }
+
+    /// Tick the fleets, jumping the clock to each wake-up, for `hours`.
+    async fn run_for(fleets: &mut Fleets, clock: &VirtualClock, hours: i64) {
+        let end = clock.now() + chrono::Duration::hours(hours);
+        while clock.now() < end {
+            fleets.process_tick().await;
+            let next = fleets
+                .next_wakeup()
+                .unwrap_or(end)
+                .max(clock.now() + chrono::Duration::seconds(1));
+            clock.set(next.min(end));
+        }
+    }
+
+    #[tokio::test]
+    async fn fleets_trip_and_spend_in_isolation() {
+        let start = Utc.with_ymd_and_hms(2025, 1, 1, 8, 0, 0).unwrap();
+        let clock = Arc::new(VirtualClock::new(start));
+        let (mut fleets, plugins) = fleets(&clock);
+        run_for(&mut fleets, &clock, 6).await;
+
+        // Alpha's failures trip only alpha's breakers, and spend nothing
+        let alpha = fleets.fleet("alpha").unwrap();
+        assert_eq!(alpha.circuit_breaker().tripped_count(), 2);
+        assert_eq!(alpha.budget().exhausted_count(), 0);
+        assert_eq!(plugins[0].executed.load(Ordering::SeqCst), 4);
+
+        // Beta's wallets of the same ids act until their budget is spent
+        let beta = fleets.fleet("beta").unwrap();
+        assert_eq!(beta.circuit_breaker().tripped_count(), 0);
+        assert_eq!(beta.budget().exhausted_count(), 2);
+        assert_eq!(plugins[1].executed.load(Ordering::SeqCst), 6);
+        assert!(beta.wallets().values().all(|w| w.consecutive_errors == 0));
+
+        // The roll-up adds up both fleets and tells their wallets apart
+        let snapshots = fleets.snapshots();
+        assert_eq!(snapshots[0].fleet_id.as_deref(), Some("alpha"));
+        assert_eq!(snapshots[0].failed_actions, 4);
+        assert_eq!(snapshots[1].successful_actions, 6);
+        let total = fleets.snapshot();
+        assert_eq!(total.total_actions, 10);
+        assert_eq!(total.successful_actions, 6);
+        assert_eq!(total.failed_actions, 4);
+        assert_eq!(total.tripped_wallets, 2);
+        assert_eq!(total.budget_exhausted_wallets, 2);
+        assert_eq!(total.actions_by_wallet.len(), 4);
+        assert_eq!(total.actions_by_wallet["alpha/w1"], 2);
+        assert_eq!(total.actions_by_wallet["beta/w1"], 3);
+        assert_eq!(total.endpoint_stats.len(), 1);
+    }
+}
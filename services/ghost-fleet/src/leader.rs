@@ -0,0 +1,517 @@
+//! Active-passive leadership of a fleet.
+//!
+//! With `[leader]` enabled, two ghost-fleet processes can run the same fleet,
+//! and only the one holding the fleet's [`Lease`] acts. The leader renews
+//! its lease every `leader.heartbeat_interval_secs` for
+//! `leader.lease_ttl_secs`; a standby takes the lease over once it expired,
+//! so a leader that stopped is replaced within the TTL and a heartbeat.
+//!
+//! Every change of holder starts a new term. A node checks with the
+//! [`LeaseStore`] that it still holds the lease of its term right before
+//! each transaction it sends (see [`Leadership::confirm`]), so a leader that
+//! stalled past its lease aborts instead of acting alongside its successor.
+//!
+//! [`FileLease`] keeps the lease in a file, for nodes on one host or a
+//! shared volume. Its file I/O blocks, so [`Leadership`] runs it on the
+//! runtime's blocking threads.
+
+use std::fmt;
+use std::io::{ErrorKind, Write as _};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use fleet_core::clock::{SharedClock, system_clock};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{FleetServiceError, Result};
+
+/// How long a [`FileLease`] waits for its lock file before giving up.
+const LOCK_ATTEMPTS: u32 = 100;
+
+/// Pause between attempts to take the lock file.
+const LOCK_RETRY: Duration = Duration::from_millis(10);
+
+/// Age after which a lock file is taken to be left behind by a crash.
+const LOCK_STALE: Duration = Duration::from_secs(5);
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// LEASE
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Leadership of a fleet, held by one node until it expires.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Lease {
+    /// Node holding the lease.
+    pub holder: String,
+
+    /// Term of the holder, one more than that of the node before it.
+    pub term: u64,
+
+    /// When the holder took the lease.
+    pub acquired_at: DateTime<Utc>,
+
+    /// When the lease runs out unless it is renewed.
+    pub expires_at: DateTime<Utc>,
+}
+
+impl Lease {
+    /// Check whether `node` holds the lease at `now`.
+    #[must_use]
+    pub fn is_held_by(&self, node: &str, now: DateTime<Utc>) -> bool {
+        self.holder == node && now < self.expires_at
+    }
+}
+
+/// Where the nodes of a fleet keep its lease.
+pub trait LeaseStore: Send + Sync + fmt::Debug {
+    /// Take the lease for `node` until `until`, or renew it if `node` holds
+    /// it, unless another node holds it at `now`.
+    ///
+    /// Returns the lease as it stands afterwards, whoever holds it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the lease cannot be read or written.
+    fn acquire(&self, node: &str, now: DateTime<Utc>, until: DateTime<Utc>) -> Result<Lease>;
+
+    /// The current lease, `None` if none was ever taken.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the lease cannot be read.
+    fn current(&self) -> Result<Option<Lease>>;
+
+    /// End `node`'s lease of `term` at `now`, so a standby need not wait for
+    /// it to expire. A lease another node took since is left alone.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the lease cannot be read or written.
+    fn release(&self, node: &str, term: u64, now: DateTime<Utc>) -> Result<()>;
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// FILE LEASE
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Lease kept as JSON in a file.
+///
+/// Updates are made under a lock file next to it, created exclusively, and
+/// written to a temporary file that is renamed over the old one. The lock
+/// file names its owner, so that a node only ever removes its own lock, and
+/// a lock left behind by a crash is taken over by renaming it aside: only
+/// one node can, and it checks that what it moved is the lock it found
+/// stale.
+#[derive(Debug, Clone)]
+pub struct FileLease {
+    path: PathBuf,
+}
+
+impl FileLease {
+    /// Keep the lease in the file at `path`.
+    #[must_use]
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    fn error(&self, e: &dyn fmt::Display) -> FleetServiceError {
+        FleetServiceError::Lease(format!("{}: {e}", self.path.display()))
+    }
+
+    fn sibling(&self, suffix: &str) -> PathBuf {
+        let mut path = self.path.as_os_str().to_owned();
+        path.push(suffix);
+        PathBuf::from(path)
+    }
+
+    fn read(&self) -> Result<Option<Lease>> {
+        match std::fs::read_to_string(&self.path) {
+            Ok(json) => serde_json::from_str(&json)
+                .map(Some)
+                .map_err(|e| self.error(&e)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(self.error(&e)),
+        }
+    }
+
+    fn write(&self, lease: &Lease) -> Result<()> {
+        let json = serde_json::to_string(lease).map_err(|e| self.error(&e))?;
+        let tmp = self.sibling(".tmp");
+        std::fs::write(&tmp, json + "\n").map_err(|e| self.error(&e))?;
+        std::fs::rename(&tmp, &self.path).map_err(|e| self.error(&e))
+    }
+
+    /// Run `update` while holding the lock file.
+    ///
+    /// Blocks, sleeping between attempts to take the lock.
+    fn locked<T>(&self, update: impl FnOnce() -> Result<T>) -> Result<T> {
+        if let Some(dir) = self.path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir).map_err(|e| self.error(&e))?;
+        }
+        let lock = self.sibling(".lock");
+        let owner = format!("{}-{:016x}", std::process::id(), rand::random::<u64>());
+        for _ in 0..LOCK_ATTEMPTS {
+            match std::fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&lock)
+            {
+                Ok(mut file) => {
+                    let result = file
+                        .write_all(owner.as_bytes())
+                        .map_err(|e| self.error(&e))
+                        .and_then(|()| update());
+                    unlock(&lock, &owner);
+                    return result;
+                }
+                Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+                    if !self.clear_stale_lock(&lock, &owner) {
+                        std::thread::sleep(LOCK_RETRY);
+                    }
+                }
+                Err(e) => return Err(self.error(&e)),
+            }
+        }
+        Err(self.error(&"lock file is held by another node"))
+    }
+
+    /// Take the lock file `lock` out of the way if a crash left it behind,
+    /// returning whether it did.
+    ///
+    /// The lock is renamed to a name of `owner`'s, which only one node
+    /// manages, and removed if it is the one found stale. A live lock that
+    /// was taken in between is put back.
+    fn clear_stale_lock(&self, lock: &Path, owner: &str) -> bool {
+        let stale = std::fs::metadata(lock)
+            .and_then(|meta| meta.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .is_some_and(|age| age > LOCK_STALE);
+        if !stale {
+            return false;
+        }
+        let found = std::fs::read_to_string(lock).unwrap_or_default();
+        let aside = self.sibling(&format!(".lock.{owner}"));
+        if std::fs::rename(lock, &aside).is_err() {
+            // Another node moved it first
+            return false;
+        }
+        let moved = std::fs::read_to_string(&aside).unwrap_or_default();
+        if moved != found {
+            // Fails if yet another lock was taken since, which then stands
+            let _ = std::fs::hard_link(&aside, lock);
+        }
+        let _ = std::fs::remove_file(&aside);
+        moved == found
+    }
+}
+
+/// Remove the lock file `lock` if `owner` still holds it.
+fn unlock(lock: &Path, owner: &str) {
+    if std::fs::read_to_string(lock).is_ok_and(|held| held == owner) {
+        let _ = std::fs::remove_file(lock);
+    }
+}
+
+impl LeaseStore for FileLease {
+    fn acquire(&self, node: &str, now: DateTime<Utc>, until: DateTime<Utc>) -> Result<Lease> {
+        self.locked(|| {
+            let lease = match self.read()? {
+                Some(lease) if lease.holder == node && now < lease.expires_at => Lease {
+                    expires_at: until,
+                    ..lease
+                },
+                Some(lease) if now < lease.expires_at => return Ok(lease),
+                previous => Lease {
+                    holder: node.to_string(),
+                    term: previous.map_or(1, |lease| lease.term + 1),
+                    acquired_at: now,
+                    expires_at: until,
+                },
+            };
+            self.write(&lease)?;
+            Ok(lease)
+        })
+    }
+
+    fn current(&self) -> Result<Option<Lease>> {
+        self.read()
+    }
+
+    fn release(&self, node: &str, term: u64, now: DateTime<Utc>) -> Result<()> {
+        self.locked(|| match self.read()? {
+            Some(lease) if lease.holder == node && lease.term == term => self.write(&Lease {
+                expires_at: now.min(lease.expires_at),
+                ..lease
+            }),
+            _ => Ok(()),
+        })
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// LEADERSHIP
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// One node's side of a fleet's lease.
+///
+/// Shared between the service, which renews the lease and acts on changes
+/// of role, and the engine, which [confirms](Self::confirm) the lease before
+/// each transaction it sends.
+#[derive(Debug)]
+pub struct Leadership {
+    /// Where the lease is kept.
+    store: Arc<dyn LeaseStore>,
+
+    /// Name of this node.
+    node: String,
+
+    /// How long the lease lasts without renewal.
+    ttl: chrono::Duration,
+
+    /// Source of the current time.
+    clock: SharedClock,
+
+    /// Lease of this node's current term, while it leads.
+    held: Mutex<Option<Lease>>,
+}
+
+impl Leadership {
+    /// Take part in the lease kept in `store` as `node`, taking it for `ttl`
+    /// at a time.
+    #[must_use]
+    pub fn new(store: Arc<dyn LeaseStore>, node: impl Into<String>, ttl: Duration) -> Self {
+        Self {
+            store,
+            node: node.into(),
+            ttl: chrono::Duration::from_std(ttl).unwrap_or(chrono::Duration::MAX),
+            clock: system_clock(),
+            held: Mutex::new(None),
+        }
+    }
+
+    /// Use `clock` instead of the system clock.
+    #[must_use]
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Name of this node.
+    #[must_use]
+    pub fn node(&self) -> &str {
+        &self.node
+    }
+
+    /// Take the lease if it is free, or renew it if this node holds it.
+    ///
+    /// Returns the lease as it stands, whoever holds it. A lease that cannot
+    /// be renewed stays held until it expires.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the lease cannot be read or written.
+    pub async fn heartbeat(&self) -> Result<Lease> {
+        let now = self.clock.now();
+        let until = now + self.ttl;
+        let lease = self
+            .with_store(move |store, node| store.acquire(node, now, until))
+            .await?;
+        self.hold(lease.is_held_by(&self.node, now).then(|| lease.clone()));
+        Ok(lease)
+    }
+
+    /// Term this node leads in, `None` if it does not hold a live lease.
+    #[must_use]
+    pub fn term(&self) -> Option<u64> {
+        let now = self.clock.now();
+        self.held
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .as_ref()
+            .filter(|lease| now < lease.expires_at)
+            .map(|lease| lease.term)
+    }
+
+    /// Check whether this node leads, as of its last heartbeat.
+    #[must_use]
+    pub fn is_leader(&self) -> bool {
+        self.term().is_some()
+    }
+
+    /// Check with the store that this node still holds the lease of its
+    /// term, right before acting on it.
+    ///
+    /// A lease that cannot be read counts as lost, and so does one another
+    /// node took over; this node then stands by until it takes the lease
+    /// again.
+    #[must_use]
+    pub async fn confirm(&self) -> bool {
+        let Some(term) = self.term() else {
+            return false;
+        };
+        let current = self.with_store(|store, _| store.current()).await;
+        let now = self.clock.now();
+        let held = matches!(
+            current,
+            Ok(Some(lease)) if lease.term == term && lease.is_held_by(&self.node, now)
+        );
+        if !held {
+            self.hold(None);
+        }
+        held
+    }
+
+    /// Give up the lease, if this node holds it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the lease cannot be written.
+    pub async fn release(&self) -> Result<()> {
+        let Some(term) = self.term() else {
+            return Ok(());
+        };
+        self.hold(None);
+        let now = self.clock.now();
+        self.with_store(move |store, node| store.release(node, term, now))
+            .await
+    }
+
+    /// Run `call` with the store and this node's name on a blocking thread,
+    /// as the store's I/O blocks.
+    async fn with_store<T: Send + 'static>(
+        &self,
+        call: impl FnOnce(&dyn LeaseStore, &str) -> Result<T> + Send + 'static,
+    ) -> Result<T> {
+        let (store, node) = (Arc::clone(&self.store), self.node.clone());
+        tokio::task::spawn_blocking(move || call(store.as_ref(), &node))
+            .await
+            .map_err(|e| FleetServiceError::Lease(format!("lease task failed: {e}")))?
+    }
+
+    fn hold(&self, lease: Option<Lease>) {
+        *self
+            .held
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner) = lease;
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use fleet_core::clock::VirtualClock;
+
+    fn nodes(dir: &tempfile::TempDir) -> (Leadership, Leadership, Arc<VirtualClock>) {
+        let clock = Arc::new(VirtualClock::new(
+            Utc.with_ymd_and_hms(2025, 1, 1, 12, 0, 0).unwrap(),
+        ));
+        let store: Arc<dyn LeaseStore> = Arc::new(FileLease::new(dir.path().join("fleet.lease")));
+        let node = |name: &str| {
+            Leadership::new(Arc::clone(&store), name, Duration::from_secs(30))
+                .with_clock(Arc::clone(&clock) as _)
+        };
+        (node("a"), node("b"), clock)
+    }
+
+    #[tokio::test]
+    async fn standby_takes_over_only_once_the_lease_expired() {
+        let dir = tempfile::tempdir().unwrap();
+        let (a, b, clock) = nodes(&dir);
+
+        assert_eq!(a.heartbeat().await.unwrap().term, 1);
+        let lease = b.heartbeat().await.unwrap();
+        assert_eq!(lease.holder, "a");
+        assert!(a.is_leader() && !b.is_leader());
+
+        // Renewals keep the term
+        clock.advance(chrono::Duration::seconds(20));
+        assert_eq!(a.heartbeat().await.unwrap().term, 1);
+        clock.advance(chrono::Duration::seconds(20));
+        b.heartbeat().await.unwrap();
+        assert!(a.confirm().await && !b.is_leader());
+
+        // A leader that stopped renewing is replaced in a new term
+        clock.advance(chrono::Duration::seconds(11));
+        assert!(!a.is_leader());
+        let lease = b.heartbeat().await.unwrap();
+        assert_eq!((lease.holder.as_str(), lease.term), ("b", 2));
+        assert!(!a.confirm().await);
+        assert_eq!(a.heartbeat().await.unwrap().holder, "b");
+        assert!(!a.is_leader());
+        assert!(!dir.path().join("fleet.lease.lock").exists());
+    }
+
+    #[tokio::test]
+    async fn confirm_notices_a_lease_taken_over_since_the_heartbeat() {
+        let dir = tempfile::tempdir().unwrap();
+        let (a, b, _) = nodes(&dir);
+        a.heartbeat().await.unwrap();
+
+        // Another node overwrote the lease while this one was not looking
+        let store = FileLease::new(dir.path().join("fleet.lease"));
+        store
+            .write(&Lease {
+                holder: "b".into(),
+                term: 2,
+                ..store.current().unwrap().unwrap()
+            })
+            .unwrap();
+
+        assert!(a.is_leader(), "as of the last heartbeat");
+        assert!(!a.confirm().await);
+        assert!(!a.is_leader());
+        assert!(!b.is_leader(), "b has not heartbeat yet");
+    }
+
+    #[tokio::test]
+    async fn released_lease_is_free_at_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let (a, b, _) = nodes(&dir);
+        a.heartbeat().await.unwrap();
+
+        a.release().await.unwrap();
+        assert!(!a.is_leader());
+        let lease = b.heartbeat().await.unwrap();
+        assert_eq!((lease.holder.as_str(), lease.term), ("b", 2));
+
+        // A node that no longer holds the lease releases nothing
+        a.release().await.unwrap();
+        assert!(b.confirm().await);
+    }
+
+    #[tokio::test]
+    async fn stale_lock_file_is_taken_over() {
+        let dir = tempfile::tempdir().unwrap();
+        let (a, _, _) = nodes(&dir);
+        let lock = dir.path().join("fleet.lease.lock");
+        let file = std::fs::File::create(&lock).unwrap();
+        file.set_modified(std::time::SystemTime::now() - LOCK_STALE * 2)
+            .unwrap();
+
+        assert_eq!(a.heartbeat().await.unwrap().holder, "a");
+        assert!(!lock.exists());
+    }
+
+    #[test]
+    fn live_lock_files_are_left_to_their_owner() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = FileLease::new(dir.path().join("fleet.lease"));
+        let lock = dir.path().join("fleet.lease.lock");
+        std::fs::write(&lock, "other").unwrap();
+
+        assert!(!store.clear_stale_lock(&lock, "me"));
+        unlock(&lock, "me");
+        assert_eq!(std::fs::read_to_string(&lock).unwrap(), "other");
+        unlock(&lock, "other");
+        assert!(!lock.exists());
+    }
+}
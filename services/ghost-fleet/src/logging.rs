@@ -0,0 +1,129 @@
+//! Redacted log output.
+//!
+//! Keys and signed transactions are [`Sensitive`](fleet_core::Sensitive)
+//! values, which never print. As a second line of defense, every line the
+//! service logs goes through [`Redacting`], which scrubs it of what looks like
+//! a private key or a raw transaction (see [`redact`]) before it is written:
+//! an error message a node echoed a request in, say.
+
+use std::io::{self, Write};
+
+use fleet_core::sensitive::redact;
+use tracing::Metadata;
+use tracing_subscriber::fmt::MakeWriter;
+
+/// Makes writers that [`redact`] each log line before passing it on to the
+/// writers of `M`.
+#[derive(Debug, Clone, Copy)]
+pub struct Redacting<M>(M);
+
+impl<M> Redacting<M> {
+    /// Redact what is written to the writers of `make_writer`.
+    #[must_use]
+    pub const fn new(make_writer: M) -> Self {
+        Self(make_writer)
+    }
+}
+
+impl<'a, M: MakeWriter<'a>> MakeWriter<'a> for Redacting<M> {
+    type Writer = RedactingWriter<M::Writer>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        RedactingWriter(self.0.make_writer())
+    }
+
+    fn make_writer_for(&'a self, meta: &Metadata<'_>) -> Self::Writer {
+        RedactingWriter(self.0.make_writer_for(meta))
+    }
+}
+
+/// Writer made by [`Redacting`].
+///
+/// The formatter writes each event whole, so a secret is never split across
+/// two writes.
+#[derive(Debug)]
+pub struct RedactingWriter<W>(W);
+
+impl<W: Write> Write for RedactingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let text = String::from_utf8_lossy(buf);
+        self.0.write_all(redact(&text).as_bytes())?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
+
+/// Log output kept in memory, for tests.
+#[cfg(test)]
+#[derive(Debug, Clone, Default)]
+pub struct Captured(std::sync::Arc<std::sync::Mutex<Vec<u8>>>);
+
+#[cfg(test)]
+impl Captured {
+    /// What was logged so far.
+    pub fn text(&self) -> String {
+        let bytes = self
+            .0
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        String::from_utf8_lossy(&bytes).into_owned()
+    }
+}
+
+#[cfg(test)]
+impl Write for Captured {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::TxHash;
+    use tracing::{info, warn};
+    use tracing_subscriber::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn log_lines_are_redacted() {
+        let captured = Captured::default();
+        let writer = captured.clone();
+        let subscriber = tracing_subscriber::registry().with(
+            tracing_subscriber::fmt::layer()
+                .json()
+                .with_writer(Redacting::new(move || writer.clone())),
+        );
+        let key = "59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d";
+        let raw_tx = format!("0x02f87083{}", "7a69".repeat(64));
+        let tx_hash = TxHash::repeat_byte(0x42);
+
+        tracing::subscriber::with_default(subscriber, || {
+            info!(%tx_hash, "Transaction sent");
+            warn!(error = %format!("invalid params: {raw_tx}"), "Failed to send");
+            warn!(key, "Key did not parse");
+        });
+
+        let logs = captured.text();
+        assert_eq!(logs.lines().count(), 3);
+        assert!(logs.contains(&tx_hash.to_string()), "{logs}");
+        assert!(!logs.contains(key), "{logs}");
+        assert!(!logs.contains(&raw_tx[..120]), "{logs}");
+        assert_eq!(logs.matches("[redacted]").count(), 2, "{logs}");
+    }
+}
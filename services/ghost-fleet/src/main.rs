@@ -12,24 +12,64 @@
 //! # Run with specific log level
 //! ghost-fleet --config config.toml --log-level debug
 //!
+//! # Run against the mainnet profile of a multi-chain config
+//! ghost-fleet --config config.toml --chain mainnet
+//!
 //! # Dry run (no transactions)
 //! ghost-fleet --config config.toml --dry-run
+//!
+//! # Validate the config and print every issue as JSON
+//! ghost-fleet --config config.toml --check-config
+//!
+//! # Simulate 30 days of fleet activity against a mock chain
+//! ghost-fleet --config config.toml --simulate --seed 42 --simulate-days 30
+//!
+//! # Manage a running fleet through its control socket
+//! ghost-fleet --config config.toml ctl status
+//! ghost-fleet --config config.toml ctl pause whale_1
+//! ghost-fleet --config config.toml ctl reset-breaker --all
+//! ghost-fleet --config config.toml ctl trigger whale_1 ghostnet.jack_in
+//! ghost-fleet --config config.toml ctl report whale_1
+//!
+//! # Review planned actions (with `[review]` enabled)
+//! ghost-fleet --config config.toml ctl plans
+//! ghost-fleet --config config.toml ctl reject 12 whale_1
+//! ghost-fleet --config config.toml ctl approve 12
 //! ```
 
 use std::path::Path;
 
+use alloy::primitives::Address;
 use anyhow::{Context, Result};
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use tokio::sync::watch;
 use tracing::{error, info, warn};
 
 mod config;
+mod control;
+mod degradation;
 mod engine;
 mod error;
+mod fleets;
+mod leader;
+mod logging;
+mod pnl;
+mod report;
+mod review;
+#[cfg(test)]
+mod resilience;
 mod service;
+mod signer;
+mod simulation;
+mod state;
+mod trigger;
 
 use config::Settings;
+use control::{ControlCommand, ControlResponse, PluginSwitch};
+use fleets::Fleets;
 use service::FleetService;
+use signer::EnvOrPrompt;
+use simulation::SimulationEngine;
 
 // ═══════════════════════════════════════════════════════════════════════════════
 // CLI ARGUMENTS
@@ -39,6 +79,7 @@ use service::FleetService;
 #[derive(Parser, Debug)]
 #[command(name = "ghost-fleet")]
 #[command(author, version, about, long_about = None)]
+#[allow(clippy::struct_excessive_bools)] // Independent flags
 struct Args {
     /// Path to configuration file
     #[arg(short, long, env = "GHOST_FLEET_CONFIG")]
@@ -48,6 +89,10 @@ struct Args {
     #[arg(short, long, env = "GHOST_FLEET_LOG_LEVEL", default_value = "info")]
     log_level: String,
 
+    /// Chain profile to run on (overrides `service.default_chain`)
+    #[arg(long, env = "GHOST_FLEET_CHAIN")]
+    chain: Option<String>,
+
     /// Dry run mode (no transactions sent)
     #[arg(long, env = "GHOST_FLEET_DRY_RUN")]
     dry_run: bool,
@@ -55,6 +100,184 @@ struct Args {
     /// Output logs as JSON
     #[arg(long, env = "GHOST_FLEET_JSON_LOGS")]
     json_logs: bool,
+
+    /// Simulate the fleet on a virtual clock against a mock chain and print a report
+    #[arg(long)]
+    simulate: bool,
+
+    /// Simulation seed (overrides `simulation.seed`)
+    #[arg(long, requires = "simulate")]
+    seed: Option<u64>,
+
+    /// Simulated days (overrides `simulation.days`)
+    #[arg(long, requires = "simulate")]
+    simulate_days: Option<u32>,
+
+    /// Validate the config, print its issues as JSON and exit
+    #[arg(long, conflicts_with = "simulate")]
+    check_config: bool,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+/// Subcommands; without one the fleet runs.
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Manage a running fleet through its control socket
+    #[command(subcommand)]
+    Ctl(CtlCommand),
+}
+
+/// Commands for a running fleet (see `[control]` in the config).
+#[derive(Subcommand, Debug)]
+enum CtlCommand {
+    /// Show the fleet snapshot and when each wallet acts next
+    Status,
+
+    /// Stop scheduling a wallet
+    Pause {
+        /// Wallet ID
+        wallet_id: String,
+    },
+
+    /// Schedule a paused wallet again
+    Resume {
+        /// Wallet ID
+        wallet_id: String,
+    },
+
+    /// Reset the circuit breaker of a wallet, or all breakers
+    ResetBreaker {
+        /// Wallet ID
+        #[arg(required_unless_present = "all")]
+        wallet_id: Option<String>,
+
+        /// Reset every wallet's breaker and the global breaker
+        #[arg(long, conflicts_with = "wallet_id")]
+        all: bool,
+    },
+
+    /// Run one action of a wallet now (breakers and budgets still apply)
+    Trigger {
+        /// Wallet ID
+        wallet_id: String,
+
+        /// Action ID, e.g. `ghostnet.jack_in`
+        action_id: String,
+    },
+
+    /// Re-read the behavior profiles from the config file
+    ReloadProfiles,
+
+    /// List the planned batches waiting for review
+    Plans,
+
+    /// Run a planned batch on the next tick
+    Approve {
+        /// Batch ID
+        batch_id: u64,
+    },
+
+    /// Drop a planned batch, or one wallet's action in it
+    Reject {
+        /// Batch ID
+        batch_id: u64,
+
+        /// Wallet ID; the whole batch if not given
+        wallet_id: Option<String>,
+    },
+
+    /// Wind a wallet down: exits only, then sweeps, then retirement
+    Retire {
+        /// Wallet ID
+        wallet_id: String,
+
+        /// Address to sweep the wallet's balances to, instead of its
+        /// configured `sweep_to`
+        #[arg(long)]
+        sweep_to: Option<Address>,
+    },
+
+    /// Show what the wallets did so far today
+    Report {
+        /// Wallet ID; all wallets if not given
+        wallet_id: Option<String>,
+
+        /// Print the report as JSON instead of a table
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Disable or enable a plugin, whatever its error rate
+    #[command(subcommand)]
+    Plugin(PluginCommand),
+}
+
+/// Switches of `ctl plugin`.
+#[derive(Subcommand, Debug)]
+enum PluginCommand {
+    /// Keep a plugin enabled, however often it fails
+    Enable {
+        /// Plugin ID, e.g. `ghostnet`
+        plugin_id: String,
+    },
+
+    /// Keep a plugin disabled; wallets fall through to the other plugins
+    Disable {
+        /// Plugin ID, e.g. `ghostnet`
+        plugin_id: String,
+    },
+
+    /// Disable a plugin only while it fails too often, as by default
+    Auto {
+        /// Plugin ID, e.g. `ghostnet`
+        plugin_id: String,
+    },
+}
+
+impl From<CtlCommand> for ControlCommand {
+    fn from(command: CtlCommand) -> Self {
+        match command {
+            CtlCommand::Status => Self::Status,
+            CtlCommand::Pause { wallet_id } => Self::Pause { wallet_id },
+            CtlCommand::Resume { wallet_id } => Self::Resume { wallet_id },
+            CtlCommand::ResetBreaker { wallet_id, .. } => Self::ResetBreaker { wallet_id },
+            CtlCommand::Trigger {
+                wallet_id,
+                action_id,
+            } => Self::Trigger {
+                wallet_id,
+                action_id,
+            },
+            CtlCommand::ReloadProfiles => Self::ReloadProfiles,
+            CtlCommand::Plans => Self::Plans,
+            CtlCommand::Approve { batch_id } => Self::Approve { batch_id },
+            CtlCommand::Reject {
+                batch_id,
+                wallet_id,
+            } => Self::Reject {
+                batch_id,
+                wallet_id,
+            },
+            CtlCommand::Retire {
+                wallet_id,
+                sweep_to,
+            } => Self::Retire {
+                wallet_id,
+                sweep_to,
+            },
+            CtlCommand::Report { wallet_id, .. } => Self::Report { wallet_id },
+            CtlCommand::Plugin(command) => {
+                let (plugin_id, switch) = match command {
+                    PluginCommand::Enable { plugin_id } => (plugin_id, PluginSwitch::Enable),
+                    PluginCommand::Disable { plugin_id } => (plugin_id, PluginSwitch::Disable),
+                    PluginCommand::Auto { plugin_id } => (plugin_id, PluginSwitch::Auto),
+                };
+                Self::Plugin { plugin_id, switch }
+            }
+        }
+    }
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -69,6 +292,13 @@ async fn main() -> Result<()> {
     // Initialize logging
     init_logging(&args.log_level, args.json_logs)?;
 
+    if let Some(Command::Ctl(command)) = args.command {
+        return ctl(&args.config, command).await;
+    }
+    if args.check_config {
+        return check_config(&args.config, args.chain.as_deref());
+    }
+
     info!(
         version = env!("CARGO_PKG_VERSION"),
         config = %args.config,
@@ -80,27 +310,81 @@ async fn main() -> Result<()> {
     check_config_permissions(&args.config);
 
     // Load configuration
-    let settings = Settings::load(&args.config)
+    let mut settings = Settings::load(&args.config)
         .with_context(|| format!("Failed to load config from {}", args.config))?;
 
+    // Resolve the chain profile and its contract addresses
+    settings
+        .select_chain(args.chain.as_deref())
+        .context("Failed to select chain")?;
+
     info!(
+        chain = %settings.chain_key(),
         chain_id = settings.chain.chain_id,
         wallets = settings.wallets.len(),
         plugins = ?settings.plugins.enabled,
         "Configuration loaded"
     );
 
-    // Validate configuration
-    settings.validate().context("Invalid configuration")?;
+    // Validate configuration, showing every issue at once
+    let report = settings.check();
+    if !report.is_empty() {
+        eprintln!("{report}");
+    }
+    anyhow::ensure!(
+        !report.has_errors(),
+        "Invalid configuration: {} errors",
+        report.errors().count()
+    );
+
+    if !settings.fleets.is_empty() {
+        anyhow::ensure!(!args.simulate, "--simulate does not support [fleet.<name>] yet");
+        return run_fleets(&settings, args.dry_run).await;
+    }
+
+    if args.simulate {
+        if let Some(seed) = args.seed {
+            settings.simulation.seed = seed;
+        }
+        if let Some(days) = args.simulate_days {
+            settings.simulation.days = days;
+        }
+
+        let engine = SimulationEngine::new(settings).context("Failed to set up simulation")?;
+        let report = Box::pin(engine.run()).await;
+        println!("{report}");
+        return Ok(());
+    }
+
+    // Load wallet keys once, prompting for passwords that are not in the environment
+    let signers =
+        signer::load_signers(&settings, &EnvOrPrompt).context("Failed to load wallet keys")?;
 
     // Create service
-    let service = FleetService::new(settings, args.dry_run)
+    let control_config = settings.control.clone();
+    let mut service = FleetService::new(settings, args.dry_run, signers)
         .await
-        .context("Failed to initialize service")?;
+        .context("Failed to initialize service")?
+        .with_config_path(&args.config);
+    service
+        .load_state()
+        .context("Failed to load the saved fleet state")?;
 
     // Set up shutdown channel
     let (shutdown_tx, shutdown_rx) = watch::channel(false);
 
+    // Serve the control socket
+    if control_config.enabled {
+        let listener = control::bind(&control_config)
+            .await
+            .context("Failed to start control socket")?;
+        let handle = service.control();
+        tokio::spawn(control::serve(listener, control_config, handle, shutdown_rx.clone()));
+    }
+
+    // Wake wallets on protocol events
+    service.watch_events(shutdown_rx.clone());
+
     // Spawn shutdown signal handler
     tokio::spawn(async move {
         wait_for_shutdown_signal().await;
@@ -108,34 +392,112 @@ async fn main() -> Result<()> {
     });
 
     // Run service until shutdown
-    service.run(shutdown_rx).await?;
+    Box::pin(service.run(shutdown_rx)).await?;
+
+    info!("Ghost Fleet stopped");
+    Ok(())
+}
+
+/// Run the fleets of `[fleet.<name>]` until shutdown.
+async fn run_fleets(settings: &Settings, dry_run: bool) -> Result<()> {
+    info!(fleets = ?settings.fleets.keys().collect::<Vec<_>>(), "Running several fleets");
+    if settings.control.enabled {
+        warn!("The control socket is not served when running several fleets");
+    }
+
+    let fleets = Fleets::new(settings, dry_run, |settings| {
+        Ok(signer::load_signers(settings, &EnvOrPrompt)?)
+    })
+    .context("Failed to initialize fleets")?;
+
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
+    tokio::spawn(async move {
+        wait_for_shutdown_signal().await;
+        let _ = shutdown_tx.send(true);
+    });
+    fleets.run(shutdown_rx).await?;
 
     info!("Ghost Fleet stopped");
     Ok(())
 }
 
+/// Validate `config` on `chain` and print its issues as JSON.
+///
+/// Fails if any issue is an error, so that scripts can rely on the exit code.
+fn check_config(config: &str, chain: Option<&str>) -> Result<()> {
+    let mut settings = Settings::load(config)
+        .with_context(|| format!("Failed to load config from {config}"))?;
+    settings.select_chain(chain).context("Failed to select chain")?;
+
+    let report = settings.check();
+    println!("{}", serde_json::to_string_pretty(&report)?);
+    anyhow::ensure!(
+        !report.has_errors(),
+        "Invalid configuration: {} errors",
+        report.errors().count()
+    );
+    Ok(())
+}
+
+/// Send a command to the control socket of the fleet running `config`, and
+/// print the answer.
+async fn ctl(config: &str, command: CtlCommand) -> Result<()> {
+    let settings = Settings::load(config)
+        .with_context(|| format!("Failed to load config from {config}"))?;
+
+    let json = matches!(command, CtlCommand::Report { json: true, .. });
+    let response = control::request(&settings.control, command.into())
+        .await
+        .context("Failed to reach the fleet's control socket")?;
+    match response {
+        ControlResponse::Status(status) => println!("{status}"),
+        ControlResponse::Done(message) => println!("{message}"),
+        ControlResponse::Triggered(result) => {
+            println!("{}", serde_json::to_string_pretty(&result)?);
+        }
+        ControlResponse::Plans(batches) if batches.is_empty() => {
+            println!("No planned batches");
+        }
+        ControlResponse::Plans(batches) => {
+            for batch in batches {
+                println!("{batch}");
+            }
+        }
+        ControlResponse::Report(report) if json => {
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        }
+        ControlResponse::Report(report) => println!("{report}"),
+        ControlResponse::Failed(error) => anyhow::bail!(error),
+    }
+    Ok(())
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // INITIALIZATION
 // ═══════════════════════════════════════════════════════════════════════════════
 
-/// Initialize the tracing subscriber for logging.
+/// Initialize the tracing subscriber for logging, with its output
+/// [redacted](logging).
 fn init_logging(level: &str, json: bool) -> Result<()> {
     use tracing_subscriber::prelude::*;
     use tracing_subscriber::{fmt, EnvFilter};
 
+    use logging::Redacting;
+
     let filter = EnvFilter::try_from_default_env()
         .unwrap_or_else(|_| EnvFilter::new(level));
+    let writer = Redacting::new(std::io::stdout);
 
     if json {
         tracing_subscriber::registry()
             .with(filter)
-            .with(fmt::layer().json())
+            .with(fmt::layer().json().with_writer(writer))
             .try_init()
             .map_err(|e| anyhow::anyhow!("Failed to init logging: {e}"))?;
     } else {
         tracing_subscriber::registry()
             .with(filter)
-            .with(fmt::layer())
+            .with(fmt::layer().with_writer(writer))
             .try_init()
             .map_err(|e| anyhow::anyhow!("Failed to init logging: {e}"))?;
     }
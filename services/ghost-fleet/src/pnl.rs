@@ -0,0 +1,617 @@
+//! Profit and loss ledger of the fleet.
+//!
+//! Every action of a wallet that was mined is recorded as a [`PnlEntry`]:
+//! the native gas its transaction paid, and the DATA it moved as its plugin
+//! reads the receipt ([`ActionPlugin::data_flows`]). A reverted transaction
+//! records its gas alone. DATA paid to a wallet outside its own transactions,
+//! such as the winnings of a bet resolved later, is recorded once its plugin
+//! reports it ([`ActionPlugin::settled_flows`]). The [`PnlLedger`] keeps
+//! running totals of each wallet, by UTC day and since the ledger started,
+//! which go into the [`FleetSnapshot`](fleet_core::metrics::FleetSnapshot),
+//! `ctl status` and the daily report.
+//!
+//! # Positions
+//!
+//! Each stake is a lot of the wallet's open position. What an exit pays is
+//! attributed to the lots it takes out, oldest first, in proportion to the
+//! stake taken out of each; the realized profit of a position is what its
+//! lots were paid less what was staked in them. A wallet that opens a new
+//! position while the ledger still holds one open lost that one without an
+//! event of its own (to a scan, say), and it is closed with nothing paid.
+//! The gas of the stakes and exits counts towards the position.
+//!
+//! # Denomination
+//!
+//! DATA and gas are kept in separate columns, in wei of each. Converting
+//! one into the other takes a price of DATA in the native token, which the
+//! fleet has no source for.
+//!
+//! # Persistence
+//!
+//! With `service.pnl_file` set, each entry is appended to the file as one
+//! JSON line, and the file is replayed when the service starts or takes
+//! over a fleet:
+//!
+//! ```text
+//! {"id":"0x5a...","wallet_id":"whale_1","at":"2025-01-01T12:00:00Z",
+//!  "action_id":"ghostnet.jack_in","gas_wei":"0x1d1a94a2000",
+//!  "flows":[{"kind":"staked","amount":"0xde0b6b3a7640000","opened":true}]}
+//! {"id":"ghostnet:hashcrash:412","wallet_id":"whale_1","at":"...",
+//!  "flows":[{"kind":"received","amount":"0x1bc16d674ec80000"}]}
+//! ```
+//!
+//! An entry is recorded once per wallet and ID, the hash of the transaction
+//! or the ID its plugin gave it, so a payout reported again after a restart
+//! is not counted twice.
+//!
+//! [`ActionPlugin::data_flows`]: fleet_core::plugins::ActionPlugin::data_flows
+//! [`ActionPlugin::settled_flows`]: fleet_core::plugins::ActionPlugin::settled_flows
+
+use std::collections::{BTreeMap, HashSet};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use alloy::primitives::{I256, U256};
+use chrono::{DateTime, NaiveDate, Utc};
+use fleet_core::metrics::{PnlSummary, PnlTotals};
+use fleet_core::plugins::DataFlow;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::error::{FleetServiceError, Result};
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// ENTRIES
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Gas and DATA flows of a wallet, recorded once.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PnlEntry {
+    /// Hash of the transaction, or the ID the plugin gave a settled flow.
+    pub id: String,
+
+    /// Wallet the flows are of.
+    pub wallet_id: String,
+
+    /// When the entry was recorded.
+    pub at: DateTime<Utc>,
+
+    /// Action the transaction executed, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub action_id: Option<String>,
+
+    /// Native gas paid (in wei).
+    #[serde(default)]
+    pub gas_wei: U256,
+
+    /// DATA moved.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub flows: Vec<DataFlow>,
+}
+
+impl PnlEntry {
+    /// What the entry adds to its wallet's totals.
+    fn totals(&self) -> PnlTotals {
+        let mut totals = PnlTotals {
+            gas_wei: self.gas_wei,
+            ..PnlTotals::default()
+        };
+        for flow in &self.flows {
+            totals.data_out = totals.data_out.saturating_add(flow.outflow());
+            totals.data_in = totals.data_in.saturating_add(flow.inflow());
+        }
+        totals
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// POSITIONS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// DATA staked into a position by one entry, and what was paid for it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lot {
+    /// DATA staked.
+    pub amount: U256,
+
+    /// Of that, DATA taken out or lost.
+    pub exited: U256,
+
+    /// DATA paid for what was taken out.
+    pub proceeds: U256,
+}
+
+impl Lot {
+    /// Stake still in the position.
+    #[must_use]
+    pub const fn open(&self) -> U256 {
+        self.amount.saturating_sub(self.exited)
+    }
+}
+
+/// A position of a wallet, as the ledger saw it staked and exited.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PositionPnl {
+    /// When the position was exited or found lost, `None` while open.
+    pub closed_at: Option<DateTime<Utc>>,
+
+    /// Stakes, oldest first.
+    pub lots: Vec<Lot>,
+
+    /// Native gas paid for the stakes and exits (in wei).
+    pub gas_wei: U256,
+}
+
+impl PositionPnl {
+    /// DATA paid for the stake taken out so far, less that stake.
+    #[must_use]
+    pub fn realized(&self) -> I256 {
+        self.lots.iter().fold(I256::ZERO, |total, lot| {
+            total
+                .saturating_add(signed(lot.proceeds))
+                .saturating_sub(signed(lot.exited))
+        })
+    }
+
+    /// Stake still in the position.
+    fn open(&self) -> U256 {
+        self.lots
+            .iter()
+            .fold(U256::ZERO, |total, lot| total.saturating_add(lot.open()))
+    }
+
+    /// Take `principal` out of the lots, oldest first, and split `proceeds`
+    /// among them by what was taken out of each.
+    fn exit(&mut self, principal: U256, proceeds: U256) {
+        let mut left = principal;
+        let mut paid = U256::ZERO;
+        for lot in self.lots.iter_mut().filter(|lot| !lot.open().is_zero()) {
+            let taken = lot.open().min(left);
+            left -= taken;
+            // The last lot gets what rounding left over
+            let share = if left.is_zero() {
+                proceeds.saturating_sub(paid)
+            } else {
+                proceeds.saturating_mul(taken) / principal
+            };
+            lot.exited += taken;
+            lot.proceeds = lot.proceeds.saturating_add(share);
+            paid = paid.saturating_add(share);
+            if left.is_zero() {
+                break;
+            }
+        }
+    }
+
+    /// Close the position at `at` with what is left of it lost.
+    fn close_lost(&mut self, at: DateTime<Utc>) {
+        for lot in &mut self.lots {
+            lot.exited = lot.amount;
+        }
+        self.closed_at = Some(at);
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// LEDGER
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Entries of one wallet, added up.
+#[derive(Debug, Clone, Default)]
+struct WalletBook {
+    /// Totals since the ledger started.
+    totals: PnlTotals,
+
+    /// Totals by UTC day.
+    daily: BTreeMap<NaiveDate, PnlTotals>,
+
+    /// Positions, oldest first; only the last can be open.
+    positions: Vec<PositionPnl>,
+}
+
+impl WalletBook {
+    /// Add `entry` to the totals and positions.
+    fn apply(&mut self, entry: &PnlEntry) {
+        let totals = entry.totals();
+        self.totals.add(&totals);
+        self.daily
+            .entry(entry.at.date_naive())
+            .or_default()
+            .add(&totals);
+
+        let mut moved_position = false;
+        for flow in &entry.flows {
+            match *flow {
+                DataFlow::Staked { amount, opened } => {
+                    self.stake(entry.at, amount, opened);
+                    moved_position = true;
+                }
+                DataFlow::Exited {
+                    principal,
+                    proceeds,
+                } => {
+                    self.exit(entry.at, principal, proceeds);
+                    moved_position = true;
+                }
+                DataFlow::Wagered { .. } | DataFlow::Received { .. } => {}
+            }
+        }
+        if moved_position && let Some(position) = self.positions.last_mut() {
+            position.gas_wei = position.gas_wei.saturating_add(entry.gas_wei);
+        }
+    }
+
+    /// The open position, if any.
+    fn open_position(&mut self) -> Option<&mut PositionPnl> {
+        self.positions
+            .last_mut()
+            .filter(|position| position.closed_at.is_none())
+    }
+
+    /// Stake `amount` into the open position, or a new one if `opened`.
+    fn stake(&mut self, at: DateTime<Utc>, amount: U256, opened: bool) {
+        if opened && let Some(lost) = self.open_position() {
+            lost.close_lost(at);
+        }
+        if self.open_position().is_none() {
+            self.positions.push(PositionPnl {
+                closed_at: None,
+                lots: Vec::new(),
+                gas_wei: U256::ZERO,
+            });
+        }
+        if let Some(position) = self.open_position() {
+            position.lots.push(Lot {
+                amount,
+                exited: U256::ZERO,
+                proceeds: U256::ZERO,
+            });
+        }
+    }
+
+    /// Take `principal` out of the open position, all of it if `None`, for
+    /// `proceeds`. The position closes once nothing is left in it.
+    ///
+    /// A position staked before the ledger started is not known to it; what
+    /// its exit paid only counts towards the totals.
+    fn exit(&mut self, at: DateTime<Utc>, principal: Option<U256>, proceeds: U256) {
+        let Some(position) = self.open_position() else {
+            return;
+        };
+        let open = position.open();
+        let principal = principal.map_or(open, |principal| principal.min(open));
+        if principal.is_zero() {
+            if let Some(lot) = position.lots.last_mut() {
+                lot.proceeds = lot.proceeds.saturating_add(proceeds);
+            }
+        } else {
+            position.exit(principal, proceeds);
+        }
+        if position.open().is_zero() {
+            position.closed_at = Some(at);
+        }
+    }
+}
+
+/// Running profit and loss of the fleet's wallets, see the
+/// [module docs](self).
+#[derive(Debug, Default)]
+pub struct PnlLedger {
+    /// File entries are appended to, if any.
+    path: Option<PathBuf>,
+
+    /// Wallet and entry IDs recorded so far.
+    recorded: HashSet<(String, String)>,
+
+    /// Entries by wallet ID, added up.
+    wallets: BTreeMap<String, WalletBook>,
+}
+
+impl PnlLedger {
+    /// A ledger kept in memory only.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The ledger kept in the file at `path`, with the entries it holds so
+    /// far. Lines that do not parse are logged and skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but cannot be read.
+    pub fn open(path: &Path) -> Result<Self> {
+        let mut ledger = Self {
+            path: Some(path.to_path_buf()),
+            ..Self::default()
+        };
+        let lines = match std::fs::read_to_string(path) {
+            Ok(lines) => lines,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(ledger),
+            Err(e) => {
+                return Err(FleetServiceError::State(format!("{}: {e}", path.display())));
+            }
+        };
+        for (number, line) in lines.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<PnlEntry>(line) {
+                Ok(entry) => {
+                    ledger.apply(&entry);
+                }
+                Err(e) => warn!(
+                    path = %path.display(),
+                    line = number + 1,
+                    error = %e,
+                    "Skipping unreadable PnL ledger entry"
+                ),
+            }
+        }
+        Ok(ledger)
+    }
+
+    /// Record `entry`, unless one with its wallet and ID was recorded
+    /// before. Returns whether it was recorded.
+    ///
+    /// A ledger file that cannot be written is logged, but does not hold up
+    /// the fleet; the entry still counts until the service stops.
+    pub fn record(&mut self, entry: &PnlEntry) -> bool {
+        if !self.apply(entry) {
+            return false;
+        }
+        if let Some(path) = &self.path {
+            let written = serde_json::to_string(entry)
+                .map_err(std::io::Error::other)
+                .and_then(|line| {
+                    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+                        std::fs::create_dir_all(dir)?;
+                    }
+                    let mut file = std::fs::OpenOptions::new()
+                        .create(true)
+                        .append(true)
+                        .open(path)?;
+                    writeln!(file, "{line}")
+                });
+            if let Err(e) = written {
+                warn!(path = %path.display(), error = %e, "Failed to write PnL ledger");
+            }
+        }
+        true
+    }
+
+    /// Add `entry` up, unless it was before. Returns whether it was new.
+    fn apply(&mut self, entry: &PnlEntry) -> bool {
+        if !self
+            .recorded
+            .insert((entry.wallet_id.clone(), entry.id.clone()))
+        {
+            return false;
+        }
+        self.wallets
+            .entry(entry.wallet_id.clone())
+            .or_default()
+            .apply(entry);
+        true
+    }
+
+    /// Totals of a wallet since the ledger started.
+    #[must_use]
+    pub fn totals(&self, wallet_id: &str) -> PnlTotals {
+        self.wallets
+            .get(wallet_id)
+            .map_or_else(PnlTotals::default, |book| book.totals)
+    }
+
+    /// Positions of a wallet, oldest first.
+    #[must_use]
+    #[allow(dead_code)] // Used in tests
+    pub fn positions(&self, wallet_id: &str) -> &[PositionPnl] {
+        self.wallets
+            .get(wallet_id)
+            .map_or(&[], |book| book.positions.as_slice())
+    }
+
+    /// Totals of all wallets, and those of `today`.
+    #[must_use]
+    pub fn summary(&self, today: NaiveDate) -> PnlSummary {
+        let mut summary = PnlSummary::default();
+        for (wallet_id, book) in &self.wallets {
+            summary.lifetime.add(&book.totals);
+            if let Some(day) = book.daily.get(&today) {
+                summary.today.add(day);
+            }
+            summary.by_wallet.insert(wallet_id.clone(), book.totals);
+            for position in book.positions.iter().filter(|p| p.closed_at.is_some()) {
+                summary.realized_data = summary.realized_data.saturating_add(position.realized());
+                summary.closed_positions += 1;
+            }
+        }
+        summary
+    }
+}
+
+/// `amount` as a signed amount, capped at [`I256::MAX`].
+fn signed(amount: U256) -> I256 {
+    I256::try_from(amount).unwrap_or(I256::MAX)
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn at(day: u32, hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 1, day, hour, 0, 0).unwrap()
+    }
+
+    fn entry(id: &str, at: DateTime<Utc>, gas_wei: u64, flows: Vec<DataFlow>) -> PnlEntry {
+        PnlEntry {
+            id: id.to_string(),
+            wallet_id: "w1".to_string(),
+            at,
+            action_id: None,
+            gas_wei: U256::from(gas_wei),
+            flows,
+        }
+    }
+
+    fn staked(amount: u64, opened: bool) -> DataFlow {
+        DataFlow::Staked {
+            amount: U256::from(amount),
+            opened,
+        }
+    }
+
+    fn exited(principal: Option<u64>, proceeds: u64) -> DataFlow {
+        DataFlow::Exited {
+            principal: principal.map(U256::from),
+            proceeds: U256::from(proceeds),
+        }
+    }
+
+    fn signed(amount: i64) -> I256 {
+        I256::try_from(amount).unwrap()
+    }
+
+    #[test]
+    fn position_lifecycle_with_gas() {
+        let mut ledger = PnlLedger::new();
+        ledger.record(&entry("0x01", at(1, 9), 10, vec![staked(100, true)]));
+        ledger.record(&entry("0x02", at(1, 10), 5, vec![staked(50, false)]));
+        // A bet and its winnings are no part of the position
+        ledger.record(&entry(
+            "0x03",
+            at(1, 11),
+            3,
+            vec![DataFlow::Wagered {
+                amount: U256::from(20),
+            }],
+        ));
+        ledger.record(&entry(
+            "hashcrash:1",
+            at(2, 8),
+            0,
+            vec![DataFlow::Received {
+                amount: U256::from(38),
+            }],
+        ));
+        // A reverted exit costs gas alone
+        ledger.record(&entry("0x04", at(2, 9), 7, vec![]));
+        ledger.record(&entry("0x05", at(2, 10), 6, vec![exited(None, 180)]));
+
+        let totals = ledger.totals("w1");
+        assert_eq!(totals.data_out, U256::from(170));
+        assert_eq!(totals.data_in, U256::from(218));
+        assert_eq!(totals.gas_wei, U256::from(31));
+        assert_eq!(totals.net_data(), signed(48));
+
+        let positions = ledger.positions("w1");
+        assert_eq!(positions.len(), 1, "one position expected");
+        let position = &positions[0];
+        assert_eq!(position.closed_at, Some(at(2, 10)));
+        assert_eq!(position.gas_wei, U256::from(21));
+        assert_eq!(position.realized(), signed(30));
+
+        let summary = ledger.summary(at(2, 0).date_naive());
+        assert_eq!(summary.lifetime, totals);
+        assert_eq!(summary.today.data_in, U256::from(218));
+        assert_eq!(summary.today.data_out, U256::ZERO);
+        assert_eq!(summary.today.gas_wei, U256::from(13));
+        assert_eq!(summary.by_wallet["w1"], totals);
+        assert_eq!(summary.realized_data, signed(30));
+        assert_eq!(summary.closed_positions, 1);
+    }
+
+    #[test]
+    fn exits_take_the_oldest_stakes_first() {
+        let mut ledger = PnlLedger::new();
+        ledger.record(&entry("0x01", at(1, 9), 0, vec![staked(100, true)]));
+        ledger.record(&entry("0x02", at(1, 10), 0, vec![staked(60, false)]));
+
+        // 120 out: all of the first lot and 20 of the second, paid pro rata
+        ledger.record(&entry("0x03", at(1, 11), 0, vec![exited(Some(120), 150)]));
+        let lots = &ledger.positions("w1")[0].lots;
+        assert_eq!(
+            (lots[0].exited, lots[0].proceeds),
+            (U256::from(100), U256::from(125))
+        );
+        assert_eq!(
+            (lots[1].exited, lots[1].proceeds),
+            (U256::from(20), U256::from(25))
+        );
+        assert_eq!(ledger.positions("w1")[0].closed_at, None);
+
+        // The rest, at a loss
+        ledger.record(&entry("0x04", at(1, 12), 0, vec![exited(None, 30)]));
+        let position = &ledger.positions("w1")[0];
+        assert_eq!(position.lots[1].exited, U256::from(60));
+        assert_eq!(position.lots[1].proceeds, U256::from(55));
+        assert_eq!(position.realized(), signed(20));
+        assert_eq!(position.closed_at, Some(at(1, 12)));
+
+        // A new position while one is open: the open one was lost
+        ledger.record(&entry("0x05", at(2, 9), 0, vec![staked(80, true)]));
+        ledger.record(&entry("0x06", at(2, 10), 0, vec![staked(40, true)]));
+        let positions = ledger.positions("w1");
+        assert_eq!(positions.len(), 3);
+        assert_eq!(positions[1].closed_at, Some(at(2, 10)));
+        assert_eq!(positions[1].realized(), signed(-80));
+        assert_eq!(positions[2].lots[0].amount, U256::from(40));
+
+        let summary = ledger.summary(at(2, 0).date_naive());
+        assert_eq!(summary.realized_data, signed(-60));
+        assert_eq!(summary.closed_positions, 2);
+    }
+
+    #[test]
+    fn restarts_do_not_count_twice() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("pnl").join("fleet.jsonl");
+
+        let mut ledger = PnlLedger::open(&path).unwrap();
+        assert!(ledger.record(&entry("0x01", at(1, 9), 10, vec![staked(100, true)])));
+        let payout = entry(
+            "hashcrash:1",
+            at(1, 10),
+            0,
+            vec![DataFlow::Received {
+                amount: U256::from(40),
+            }],
+        );
+        assert!(ledger.record(&payout));
+        assert!(!ledger.record(&payout));
+        let before = ledger.summary(at(1, 0).date_naive());
+
+        // The same payout reported again after a restart is not recorded
+        let mut ledger = PnlLedger::open(&path).unwrap();
+        assert_eq!(ledger.summary(at(1, 0).date_naive()), before);
+        assert!(!ledger.record(&payout));
+        assert!(ledger.record(&entry("0x02", at(1, 11), 5, vec![exited(None, 90)])));
+
+        // Another wallet's payout of the same ID is its own, and lines that
+        // do not parse are skipped
+        let mut other = entry("hashcrash:1", at(1, 12), 0, vec![]);
+        other.wallet_id = "w2".to_string();
+        assert!(ledger.record(&other));
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap();
+        writeln!(file, "{{\"id\":").unwrap();
+
+        let ledger = PnlLedger::open(&path).unwrap();
+        let totals = ledger.totals("w1");
+        assert_eq!(totals.data_out, U256::from(100));
+        assert_eq!(totals.data_in, U256::from(130));
+        assert_eq!(totals.gas_wei, U256::from(15));
+        assert_eq!(ledger.positions("w1")[0].realized(), signed(-10));
+        assert_eq!(ledger.summary(at(1, 0).date_naive()).by_wallet.len(), 2);
+    }
+}
@@ -0,0 +1,986 @@
+//! Daily wallet activity reports.
+//!
+//! A [`ReportSnapshot`] is what every wallet has done since the service
+//! started, as counted by the [`FleetMetrics`](fleet_core::metrics::FleetMetrics),
+//! the wallet's spend ledger, the circuit breaker, the plugins'
+//! [activity](fleet_core::plugins::ActionPlugin::activity) and the
+//! [PnL ledger](crate::pnl). A [`DailyReport`] is the difference of two
+//! snapshots.
+//!
+//! With `report.enabled` set, the [`ReportGenerator`] closes each day at
+//! `report.hour_utc`: the service takes a snapshot on its loop, and the
+//! generator diffs it against the previous one on a task of its own, so the
+//! fleet never waits on a report. Each report is written to `report.dir` as
+//! `<date>.json` and a `<date>.txt` table (`<fleet>-<date>.*` for one of
+//! several fleets), and POSTed to `report.webhook_url` if set. Failed POSTs
+//! are retried with a doubling delay; a report larger than
+//! `report.webhook_max_bytes` is posted without its wallet rows.
+//!
+//! `ctl report [wallet]` shows the report of the day so far, whether or not
+//! daily reports are enabled.
+//!
+//! # Anomalies
+//!
+//! A report flags wallets that failed more than three times as often as the
+//! fleet's average wallet, and wallets that were active but did nothing over
+//! a period of at least 12 hours.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use alloy::primitives::{I256, U256};
+use chrono::{DateTime, NaiveTime, Utc};
+use fleet_core::metrics::PnlTotals;
+use serde::{Deserialize, Serialize};
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+use crate::config::ReportConfig;
+use crate::error::{FleetServiceError, Result};
+
+/// A wallet failing more than this many times as often as the fleet's
+/// average wallet is flagged.
+const FAILURE_FACTOR: f64 = 3.0;
+
+/// Shortest period over which an active wallet doing nothing is flagged.
+const MIN_IDLE_PERIOD: chrono::Duration = chrono::Duration::hours(12);
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// SNAPSHOTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Counts and DATA flows of a wallet, or of the fleet.
+///
+/// In a [`ReportSnapshot`] they are cumulative; in a [`DailyReport`] they
+/// cover the report's period. Amounts are in wei.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Activity {
+    /// Actions attempted, skipped or simulated.
+    pub actions: u64,
+
+    /// Actions that succeeded on chain.
+    pub successes: u64,
+
+    /// Actions that reverted or were dropped.
+    pub failures: u64,
+
+    /// Native gas spent.
+    pub gas_wei: U256,
+
+    /// DATA put at stake.
+    pub staked: U256,
+
+    /// DATA taken back out.
+    pub extracted: U256,
+
+    /// DATA won.
+    pub won: U256,
+
+    /// DATA lost.
+    pub lost: U256,
+
+    /// DATA received, as the PnL ledger counts it.
+    pub data_in: U256,
+
+    /// DATA paid out, as the PnL ledger counts it.
+    pub data_out: U256,
+
+    /// Times the circuit breaker tripped.
+    pub breaker_trips: u64,
+}
+
+impl Activity {
+    /// What was added to `start` to reach `self`.
+    #[must_use]
+    pub const fn since(&self, start: &Self) -> Self {
+        Self {
+            actions: self.actions.saturating_sub(start.actions),
+            successes: self.successes.saturating_sub(start.successes),
+            failures: self.failures.saturating_sub(start.failures),
+            gas_wei: self.gas_wei.saturating_sub(start.gas_wei),
+            staked: self.staked.saturating_sub(start.staked),
+            extracted: self.extracted.saturating_sub(start.extracted),
+            won: self.won.saturating_sub(start.won),
+            lost: self.lost.saturating_sub(start.lost),
+            data_in: self.data_in.saturating_sub(start.data_in),
+            data_out: self.data_out.saturating_sub(start.data_out),
+            breaker_trips: self.breaker_trips.saturating_sub(start.breaker_trips),
+        }
+    }
+
+    /// Add `other` to this activity.
+    pub const fn add(&mut self, other: &Self) {
+        self.actions += other.actions;
+        self.successes += other.successes;
+        self.failures += other.failures;
+        self.gas_wei = self.gas_wei.saturating_add(other.gas_wei);
+        self.staked = self.staked.saturating_add(other.staked);
+        self.extracted = self.extracted.saturating_add(other.extracted);
+        self.won = self.won.saturating_add(other.won);
+        self.lost = self.lost.saturating_add(other.lost);
+        self.data_in = self.data_in.saturating_add(other.data_in);
+        self.data_out = self.data_out.saturating_add(other.data_out);
+        self.breaker_trips += other.breaker_trips;
+    }
+
+    /// DATA received less DATA paid out, see [`PnlTotals::net_data`].
+    #[must_use]
+    pub fn net_data(&self) -> I256 {
+        PnlTotals {
+            data_out: self.data_out,
+            data_in: self.data_in,
+            gas_wei: self.gas_wei,
+        }
+        .net_data()
+    }
+
+    /// Successes as a percentage of the actions that succeeded or failed;
+    /// 100 if none did, like
+    /// [`FleetMetrics::success_rate`](fleet_core::metrics::FleetMetrics::success_rate).
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)] // Acceptable for a report
+    pub fn success_rate_pct(&self) -> f64 {
+        let attempted = self.successes + self.failures;
+        if attempted == 0 {
+            100.0
+        } else {
+            self.successes as f64 / attempted as f64 * 100.0
+        }
+    }
+}
+
+/// A wallet as read for a [`ReportSnapshot`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WalletReading {
+    /// Everything the wallet did since the service started.
+    pub activity: Activity,
+
+    /// Whether the wallet is meant to act: enabled, not AFK, not retired.
+    pub active: bool,
+
+    /// Positions the wallet holds.
+    pub positions: Vec<String>,
+}
+
+/// Every wallet's activity at one point in time.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReportSnapshot {
+    /// When the snapshot was taken.
+    pub taken_at: DateTime<Utc>,
+
+    /// Readings by wallet ID.
+    pub wallets: BTreeMap<String, WalletReading>,
+}
+
+impl ReportSnapshot {
+    /// A snapshot of no wallets.
+    #[must_use]
+    pub const fn empty(taken_at: DateTime<Utc>) -> Self {
+        Self {
+            taken_at,
+            wallets: BTreeMap::new(),
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// REPORTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// What a wallet did over a report's period.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WalletReport {
+    /// Wallet ID.
+    pub wallet_id: String,
+
+    /// Whether the wallet is meant to act, at the end of the period.
+    pub active: bool,
+
+    /// Counts and DATA flows.
+    #[serde(flatten)]
+    pub activity: Activity,
+
+    /// See [`Activity::success_rate_pct`].
+    pub success_rate_pct: f64,
+
+    /// Positions held at the end of the period.
+    pub positions: Vec<String>,
+}
+
+/// What the fleet did over a report's period.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FleetTotals {
+    /// Wallets reported on.
+    pub wallets: usize,
+
+    /// Of those, wallets meant to act.
+    pub active_wallets: usize,
+
+    /// Counts and DATA flows of all wallets.
+    #[serde(flatten)]
+    pub activity: Activity,
+
+    /// See [`Activity::success_rate_pct`].
+    pub success_rate_pct: f64,
+}
+
+/// A wallet behaving unlike the rest of the fleet.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Anomaly {
+    /// The wallet failed far more often than the fleet's average wallet.
+    Failures {
+        /// Wallet ID.
+        wallet_id: String,
+        /// Failed actions of the wallet.
+        failures: u64,
+        /// Failed actions of the average wallet.
+        fleet_average: f64,
+    },
+
+    /// The wallet was meant to act, but did nothing.
+    Idle {
+        /// Wallet ID.
+        wallet_id: String,
+    },
+}
+
+impl Anomaly {
+    /// Wallet the anomaly is of.
+    #[must_use]
+    pub fn wallet_id(&self) -> &str {
+        match self {
+            Self::Failures { wallet_id, .. } | Self::Idle { wallet_id } => wallet_id,
+        }
+    }
+}
+
+impl fmt::Display for Anomaly {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Failures {
+                wallet_id,
+                failures,
+                fleet_average,
+            } => write!(
+                f,
+                "{wallet_id}: {failures} failures, fleet average {fleet_average:.1}"
+            ),
+            Self::Idle { wallet_id } => write!(f, "{wallet_id}: active but idle"),
+        }
+    }
+}
+
+/// Activity of the fleet's wallets between two snapshots, see the
+/// [module docs](self).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DailyReport {
+    /// Fleet reported on, if one of several.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fleet: Option<String>,
+
+    /// Start of the period.
+    pub from: DateTime<Utc>,
+
+    /// End of the period.
+    pub to: DateTime<Utc>,
+
+    /// Fleet totals, of all wallets.
+    pub totals: FleetTotals,
+
+    /// Wallets behaving unlike the rest of the fleet.
+    pub anomalies: Vec<Anomaly>,
+
+    /// Wallets, by ID.
+    pub wallets: Vec<WalletReport>,
+
+    /// Whether the wallet rows were left out to keep the report small.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub truncated: bool,
+}
+
+impl DailyReport {
+    /// The activity between `start` and `end`.
+    ///
+    /// Wallets missing from `start`, such as standby wallets activated in
+    /// the meantime, count from zero.
+    #[must_use]
+    pub fn between(start: &ReportSnapshot, end: &ReportSnapshot, fleet: Option<String>) -> Self {
+        let mut totals = FleetTotals {
+            wallets: end.wallets.len(),
+            ..FleetTotals::default()
+        };
+        let wallets: Vec<_> = end
+            .wallets
+            .iter()
+            .map(|(id, reading)| {
+                let activity = start.wallets.get(id).map_or(reading.activity, |earlier| {
+                    reading.activity.since(&earlier.activity)
+                });
+                totals.activity.add(&activity);
+                totals.active_wallets += usize::from(reading.active);
+                WalletReport {
+                    wallet_id: id.clone(),
+                    active: reading.active,
+                    success_rate_pct: activity.success_rate_pct(),
+                    activity,
+                    positions: reading.positions.clone(),
+                }
+            })
+            .collect();
+        totals.success_rate_pct = totals.activity.success_rate_pct();
+
+        let anomalies = anomalies(&wallets, end.taken_at - start.taken_at);
+        Self {
+            fleet,
+            from: start.taken_at,
+            to: end.taken_at,
+            totals,
+            anomalies,
+            wallets,
+            truncated: false,
+        }
+    }
+
+    /// The report with only `wallet_id`'s row and anomalies; the totals stay
+    /// those of the fleet.
+    #[must_use]
+    pub fn for_wallet(mut self, wallet_id: &str) -> Self {
+        self.wallets.retain(|wallet| wallet.wallet_id == wallet_id);
+        self.anomalies
+            .retain(|anomaly| anomaly.wallet_id() == wallet_id);
+        self
+    }
+
+    /// File name of the report, without extension.
+    fn file_stem(&self) -> String {
+        let date = self.from.format("%Y-%m-%d");
+        self.fleet
+            .as_ref()
+            .map_or_else(|| date.to_string(), |fleet| format!("{fleet}-{date}"))
+    }
+}
+
+/// Flag the wallets that failed far more often than the average, and the
+/// active ones that did nothing over a long enough `period`.
+#[allow(clippy::cast_precision_loss)] // Acceptable for a report
+fn anomalies(wallets: &[WalletReport], period: chrono::Duration) -> Vec<Anomaly> {
+    let failures: u64 = wallets.iter().map(|wallet| wallet.activity.failures).sum();
+    let fleet_average = failures as f64 / wallets.len().max(1) as f64;
+
+    let mut anomalies = Vec::new();
+    for wallet in wallets {
+        let failed = wallet.activity.failures;
+        if failed > 0 && failed as f64 > fleet_average * FAILURE_FACTOR {
+            anomalies.push(Anomaly::Failures {
+                wallet_id: wallet.wallet_id.clone(),
+                failures: failed,
+                fleet_average,
+            });
+        }
+        if wallet.active && wallet.activity.actions == 0 && period >= MIN_IDLE_PERIOD {
+            anomalies.push(Anomaly::Idle {
+                wallet_id: wallet.wallet_id.clone(),
+            });
+        }
+    }
+    anomalies
+}
+
+/// A table of the wallets and the fleet totals, then the anomalies:
+///
+/// ```text
+/// Activity 2025-01-01 00:00 to 2025-01-02 00:00 UTC
+/// WALLET  ACTIONS  SUCCESS  FAILED  GAS (WEI)  STAKED  EXTRACTED  WON  LOST  NET DATA  TRIPS
+/// w1      12       91.7%    1       ...
+/// TOTAL   ...
+///
+/// Anomalies:
+///   w2: active but idle
+/// ```
+impl fmt::Display for DailyReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const HEADER: [&str; 11] = [
+            "WALLET",
+            "ACTIONS",
+            "SUCCESS",
+            "FAILED",
+            "GAS (WEI)",
+            "STAKED",
+            "EXTRACTED",
+            "WON",
+            "LOST",
+            "NET DATA",
+            "TRIPS",
+        ];
+        let row = |name: &str, activity: &Activity, success_rate_pct: f64| {
+            [
+                name.to_string(),
+                activity.actions.to_string(),
+                format!("{success_rate_pct:.1}%"),
+                activity.failures.to_string(),
+                activity.gas_wei.to_string(),
+                activity.staked.to_string(),
+                activity.extracted.to_string(),
+                activity.won.to_string(),
+                activity.lost.to_string(),
+                activity.net_data().to_string(),
+                activity.breaker_trips.to_string(),
+            ]
+        };
+        let mut rows: Vec<_> = self
+            .wallets
+            .iter()
+            .map(|wallet| row(&wallet.wallet_id, &wallet.activity, wallet.success_rate_pct))
+            .collect();
+        rows.push(row(
+            "TOTAL",
+            &self.totals.activity,
+            self.totals.success_rate_pct,
+        ));
+
+        let mut widths = HEADER.map(str::len);
+        for row in &rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.len());
+            }
+        }
+
+        write!(
+            f,
+            "Activity {} to {} UTC",
+            self.from.format("%Y-%m-%d %H:%M"),
+            self.to.format("%Y-%m-%d %H:%M")
+        )?;
+        if let Some(fleet) = &self.fleet {
+            write!(f, " (fleet {fleet})")?;
+        }
+        let header = HEADER.map(String::from);
+        for cells in std::iter::once(&header).chain(&rows) {
+            let line: Vec<_> = cells
+                .iter()
+                .zip(widths)
+                .map(|(cell, width)| format!("{cell:<width$}"))
+                .collect();
+            write!(f, "\n{}", line.join("  ").trim_end())?;
+        }
+        if self.truncated {
+            write!(f, "\n(wallet rows left out)")?;
+        }
+        if !self.anomalies.is_empty() {
+            write!(f, "\n\nAnomalies:")?;
+            for anomaly in &self.anomalies {
+                write!(f, "\n  {anomaly}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// GENERATOR
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Closes each day into a [`DailyReport`], see the [module docs](self).
+#[derive(Debug)]
+pub struct ReportGenerator {
+    /// Report settings.
+    config: ReportConfig,
+
+    /// Fleet the reports are of, if one of several.
+    fleet: Option<String>,
+
+    /// Snapshot the current day started with.
+    baseline: ReportSnapshot,
+
+    /// When the current day closes.
+    next_run: DateTime<Utc>,
+
+    /// Client for the webhook.
+    client: reqwest::Client,
+}
+
+impl ReportGenerator {
+    /// Create a generator whose first day starts with `baseline`.
+    #[must_use]
+    pub fn new(config: &ReportConfig, baseline: ReportSnapshot) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.webhook_timeout_secs))
+            .build()
+            .unwrap_or_default();
+        Self {
+            next_run: next_run_after(baseline.taken_at, config.hour_utc),
+            config: config.clone(),
+            fleet: None,
+            baseline,
+            client,
+        }
+    }
+
+    /// Label the reports with `fleet`.
+    #[must_use]
+    pub fn with_fleet(mut self, fleet: &str) -> Self {
+        self.fleet = Some(fleet.to_string());
+        self
+    }
+
+    /// Start the current day over with `baseline`, e.g. once the wallets'
+    /// state is loaded.
+    pub fn rebase(&mut self, baseline: ReportSnapshot) {
+        self.next_run = next_run_after(baseline.taken_at, self.config.hour_utc);
+        self.baseline = baseline;
+    }
+
+    /// Check whether the current day should be closed at `now`.
+    #[must_use]
+    pub fn is_due_at(&self, now: DateTime<Utc>) -> bool {
+        self.config.enabled && now >= self.next_run
+    }
+
+    /// Report of the current day up to `current`.
+    #[must_use]
+    pub fn so_far(&self, current: &ReportSnapshot) -> DailyReport {
+        DailyReport::between(&self.baseline, current, self.fleet.clone())
+    }
+
+    /// Close the current day with `current` and start the next one with it.
+    ///
+    /// The report is built, written and posted on a task of its own; the
+    /// returned handle resolves once it is done. Failures are logged.
+    pub fn close_day(&mut self, current: ReportSnapshot) -> JoinHandle<()> {
+        self.next_run = next_run_after(current.taken_at, self.config.hour_utc);
+        let start = std::mem::replace(&mut self.baseline, current.clone());
+        let config = self.config.clone();
+        let client = self.client.clone();
+        let fleet = self.fleet.clone();
+        tokio::spawn(async move {
+            let report = DailyReport::between(&start, &current, fleet);
+            deliver(&config, &client, &report).await;
+        })
+    }
+}
+
+/// The first time after `after` that is `hour` o'clock UTC.
+fn next_run_after(after: DateTime<Utc>, hour: u32) -> DateTime<Utc> {
+    let time = NaiveTime::from_hms_opt(hour.min(23), 0, 0).unwrap_or(NaiveTime::MIN);
+    let today = after.date_naive().and_time(time).and_utc();
+    if today > after {
+        today
+    } else {
+        today + chrono::Duration::days(1)
+    }
+}
+
+/// Write `report` to the report directory and post it to the webhook.
+async fn deliver(config: &ReportConfig, client: &reqwest::Client, report: &DailyReport) {
+    match write(&config.dir, report).await {
+        Ok(path) => info!(
+            path = %path.display(),
+            wallets = report.wallets.len(),
+            anomalies = report.anomalies.len(),
+            "Wrote activity report"
+        ),
+        Err(e) => warn!(error = %e, "Failed to write activity report"),
+    }
+    if let Some(url) = &config.webhook_url
+        && let Err(e) = post(config, client, url, report).await
+    {
+        warn!(error = %e, "Failed to post activity report");
+    }
+}
+
+/// Write `report` to `dir` as JSON and as a table, returning the JSON file's
+/// path.
+///
+/// # Errors
+///
+/// Returns an error if the directory or a file cannot be written.
+pub async fn write(dir: &Path, report: &DailyReport) -> Result<PathBuf> {
+    let io = |e: std::io::Error| FleetServiceError::Report(format!("{}: {e}", dir.display()));
+    tokio::fs::create_dir_all(dir).await.map_err(io)?;
+
+    let stem = report.file_stem();
+    let json = serde_json::to_string_pretty(report)
+        .map_err(|e| FleetServiceError::Report(e.to_string()))?;
+    let path = dir.join(format!("{stem}.json"));
+    tokio::fs::write(&path, json + "\n").await.map_err(io)?;
+    tokio::fs::write(dir.join(format!("{stem}.txt")), format!("{report}\n"))
+        .await
+        .map_err(io)?;
+    Ok(path)
+}
+
+/// JSON body of `report` of at most `max_bytes`, without the wallet rows if
+/// needed.
+fn webhook_body(report: &DailyReport, max_bytes: usize) -> Result<Vec<u8>> {
+    let encode = |report: &DailyReport| {
+        serde_json::to_vec(report).map_err(|e| FleetServiceError::Report(e.to_string()))
+    };
+    let body = encode(report)?;
+    if body.len() <= max_bytes {
+        return Ok(body);
+    }
+    let truncated = DailyReport {
+        wallets: Vec::new(),
+        truncated: true,
+        ..report.clone()
+    };
+    let body = encode(&truncated)?;
+    if body.len() <= max_bytes {
+        return Ok(body);
+    }
+    Err(FleetServiceError::Report(format!(
+        "report is {} bytes without its wallet rows, over the {max_bytes} byte cap",
+        body.len()
+    )))
+}
+
+/// POST `report` to `url`, retrying connection errors, 429s and 5xx
+/// responses with a doubling delay.
+///
+/// # Errors
+///
+/// Returns an error if the report is too large, the webhook rejects it, or
+/// every attempt fails.
+pub async fn post(
+    config: &ReportConfig,
+    client: &reqwest::Client,
+    url: &str,
+    report: &DailyReport,
+) -> Result<()> {
+    let body = webhook_body(report, config.webhook_max_bytes)?;
+    let mut delay = Duration::from_millis(config.webhook_retry_delay_ms);
+    let mut attempt = 0;
+    loop {
+        let response = client
+            .post(url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body.clone())
+            .send()
+            .await;
+        let error = match response {
+            Ok(response) if response.status().is_success() => return Ok(()),
+            Ok(response) => {
+                let status = response.status();
+                if !(status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS) {
+                    return Err(FleetServiceError::Report(format!(
+                        "webhook answered {status}"
+                    )));
+                }
+                format!("webhook answered {status}")
+            }
+            Err(e) => e.to_string(),
+        };
+        if attempt >= config.webhook_retries {
+            return Err(FleetServiceError::Report(format!(
+                "{error}, gave up after {} attempts",
+                attempt + 1
+            )));
+        }
+        attempt += 1;
+        warn!(attempt, error = %error, "Activity report not posted, retrying");
+        tokio::time::sleep(delay).await;
+        delay *= 2;
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use chrono::TimeZone;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use super::*;
+
+    fn at(hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap() + chrono::Duration::hours(hour.into())
+    }
+
+    fn reading(actions: u64, failures: u64, staked: u64) -> WalletReading {
+        WalletReading {
+            activity: Activity {
+                actions,
+                successes: actions - failures,
+                failures,
+                gas_wei: U256::from(actions * 1000),
+                staked: U256::from(staked),
+                ..Activity::default()
+            },
+            active: true,
+            positions: Vec::new(),
+        }
+    }
+
+    fn snapshot(taken_at: DateTime<Utc>, wallets: &[(&str, WalletReading)]) -> ReportSnapshot {
+        ReportSnapshot {
+            taken_at,
+            wallets: wallets
+                .iter()
+                .map(|(id, reading)| ((*id).to_string(), reading.clone()))
+                .collect(),
+        }
+    }
+
+    /// A day of eleven wallets, one failing a lot and one doing nothing.
+    fn day() -> DailyReport {
+        let mut start = snapshot(at(0), &[("busy", reading(10, 0, 500))]);
+        let mut end = snapshot(
+            at(24),
+            &[
+                ("busy", reading(14, 1, 800)),
+                ("failing", reading(10, 8, 0)),
+                ("idle", reading(0, 0, 0)),
+            ],
+        );
+        for i in 0..8 {
+            start.wallets.insert(format!("w{i}"), reading(0, 0, 0));
+            end.wallets
+                .insert(format!("w{i}"), reading(4, u64::from(i == 0), 100));
+        }
+        DailyReport::between(&start, &end, None)
+    }
+
+    #[test]
+    fn reports_the_difference_of_two_snapshots() {
+        let report = day();
+        let busy = report
+            .wallets
+            .iter()
+            .find(|w| w.wallet_id == "busy")
+            .unwrap();
+        assert_eq!((busy.activity.actions, busy.activity.failures), (4, 1));
+        assert_eq!(busy.activity.staked, U256::from(300));
+        assert_eq!(busy.activity.gas_wei, U256::from(4000));
+        assert!((busy.success_rate_pct - 75.0).abs() < f64::EPSILON);
+
+        // Wallets new since the start count from zero
+        let failing = report
+            .wallets
+            .iter()
+            .find(|w| w.wallet_id == "failing")
+            .unwrap();
+        assert_eq!(failing.activity.failures, 8);
+
+        assert_eq!(
+            (report.totals.wallets, report.totals.active_wallets),
+            (11, 11)
+        );
+        assert_eq!(report.totals.activity.actions, 4 + 8 * 4 + 10);
+        assert_eq!(report.totals.activity.failures, 1 + 1 + 8);
+        assert_eq!(report.totals.activity.staked, U256::from(300 + 800));
+
+        // Net DATA of the period, from the ledger's totals
+        let mut start = reading(0, 0, 0);
+        start.activity.data_out = U256::from(100);
+        let mut end = start.clone();
+        end.activity.data_in = U256::from(40);
+        end.activity.data_out = U256::from(150);
+        let report = DailyReport::between(
+            &snapshot(at(0), &[("w", start)]),
+            &snapshot(at(24), &[("w", end)]),
+            None,
+        );
+        assert_eq!(
+            report.totals.activity.net_data(),
+            I256::try_from(-10i64).unwrap()
+        );
+    }
+
+    #[test]
+    fn flags_failing_and_idle_wallets() {
+        let report = day();
+        // 10 failures over 11 wallets: only 8 is over three times the average
+        assert_eq!(
+            report.anomalies,
+            [
+                Anomaly::Failures {
+                    wallet_id: "failing".to_string(),
+                    failures: 8,
+                    fleet_average: 10.0 / 11.0,
+                },
+                Anomaly::Idle {
+                    wallet_id: "idle".to_string(),
+                },
+            ]
+        );
+
+        // Not over a short period, nor for paused wallets
+        let start = snapshot(at(0), &[]);
+        let mut paused = reading(0, 0, 0);
+        paused.active = false;
+        let end = snapshot(
+            at(2),
+            &[("idle", reading(0, 0, 0)), ("paused", paused.clone())],
+        );
+        assert!(
+            DailyReport::between(&start, &end, None)
+                .anomalies
+                .is_empty()
+        );
+        let end = snapshot(at(12), &[("idle", reading(0, 0, 0)), ("paused", paused)]);
+        let flagged: Vec<_> = DailyReport::between(&start, &end, None)
+            .anomalies
+            .iter()
+            .map(|anomaly| anomaly.wallet_id().to_string())
+            .collect();
+        assert_eq!(flagged, ["idle"]);
+    }
+
+    #[test]
+    fn renders_a_table_and_roundtrips() {
+        let report = day().for_wallet("failing");
+        assert_eq!(report.wallets.len(), 1);
+        assert_eq!(report.anomalies.len(), 1);
+
+        let table = report.to_string();
+        let lines: Vec<_> = table.lines().collect();
+        assert_eq!(
+            lines[0],
+            "Activity 2025-01-01 00:00 to 2025-01-02 00:00 UTC"
+        );
+        assert!(lines[1].starts_with("WALLET   ACTIONS  SUCCESS  FAILED"));
+        assert!(lines[2].starts_with("failing  10       20.0%    8"));
+        assert!(lines[3].starts_with("TOTAL    46       "));
+        assert_eq!(lines[6], "  failing: 8 failures, fleet average 0.9");
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["wallets"][0]["failures"], 8);
+        assert_eq!(json["anomalies"][0]["kind"], "failures");
+        assert!(json.get("truncated").is_none());
+        let restored: DailyReport = serde_json::from_value(json).unwrap();
+        assert_eq!(restored, report);
+    }
+
+    #[test]
+    fn days_close_at_the_configured_hour() {
+        assert_eq!(next_run_after(at(3), 6), at(6));
+        assert_eq!(next_run_after(at(6), 6), at(30));
+        assert_eq!(next_run_after(at(7), 0), at(24));
+
+        let config = ReportConfig {
+            enabled: true,
+            hour_utc: 6,
+            ..ReportConfig::default()
+        };
+        let generator = ReportGenerator::new(&config, ReportSnapshot::empty(at(3)));
+        assert!(!generator.is_due_at(at(5)));
+        assert!(generator.is_due_at(at(6)));
+
+        let disabled = ReportGenerator::new(&ReportConfig::default(), ReportSnapshot::empty(at(3)));
+        assert!(!disabled.is_due_at(at(48)));
+    }
+
+    #[tokio::test]
+    async fn closed_days_are_written_and_start_the_next() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = ReportConfig {
+            enabled: true,
+            dir: dir.path().join("reports"),
+            ..ReportConfig::default()
+        };
+        let start = snapshot(at(0), &[("w1", reading(2, 0, 10))]);
+        let mut generator = ReportGenerator::new(&config, start).with_fleet("alpha");
+
+        let end = snapshot(at(24), &[("w1", reading(5, 1, 40))]);
+        generator.close_day(end.clone()).await.unwrap();
+        assert!(!generator.is_due_at(at(47)));
+        assert_eq!(generator.so_far(&end).totals.activity.actions, 0);
+
+        let json = std::fs::read_to_string(config.dir.join("alpha-2025-01-01.json")).unwrap();
+        let report: DailyReport = serde_json::from_str(&json).unwrap();
+        assert_eq!(report.fleet.as_deref(), Some("alpha"));
+        assert_eq!(report.wallets[0].activity.actions, 3);
+        let table = std::fs::read_to_string(config.dir.join("alpha-2025-01-01.txt")).unwrap();
+        assert!(table.contains("(fleet alpha)"));
+    }
+
+    fn webhook_config(server: &MockServer) -> ReportConfig {
+        ReportConfig {
+            webhook_url: Some(format!("{}/reports", server.uri())),
+            webhook_retries: 2,
+            webhook_retry_delay_ms: 1,
+            ..ReportConfig::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn webhook_posts_are_retried() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/reports"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(2)
+            .expect(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/reports"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let config = webhook_config(&server);
+        let url = config.webhook_url.clone().unwrap();
+        post(&config, &reqwest::Client::new(), &url, &day())
+            .await
+            .unwrap();
+
+        let requests = server.received_requests().await.unwrap();
+        let body: DailyReport = serde_json::from_slice(&requests[2].body).unwrap();
+        assert_eq!(body.wallets.len(), 11);
+    }
+
+    #[tokio::test]
+    async fn webhook_gives_up_on_rejections_and_exhaustion() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(400))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let config = webhook_config(&server);
+        let url = config.webhook_url.clone().unwrap();
+        let client = reqwest::Client::new();
+        assert!(post(&config, &client, &url, &day()).await.is_err());
+        server.verify().await;
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(429))
+            .expect(3)
+            .mount(&server)
+            .await;
+        let config = webhook_config(&server);
+        let url = config.webhook_url.clone().unwrap();
+        let error = post(&config, &client, &url, &day()).await.unwrap_err();
+        assert!(
+            error.to_string().contains("gave up after 3 attempts"),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn oversized_reports_drop_their_wallet_rows() {
+        let report = day();
+        let full = webhook_body(&report, usize::MAX).unwrap();
+
+        let body = webhook_body(&report, full.len() - 1).unwrap();
+        let sent: DailyReport = serde_json::from_slice(&body).unwrap();
+        assert!(sent.truncated && sent.wallets.is_empty());
+        assert_eq!(sent.totals, report.totals);
+        let flagged = |report: &DailyReport| -> Vec<String> {
+            report.anomalies.iter().map(ToString::to_string).collect()
+        };
+        assert_eq!(flagged(&sent), flagged(&report));
+
+        assert!(webhook_body(&report, 16).is_err());
+    }
+}
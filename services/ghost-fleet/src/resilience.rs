@@ -0,0 +1,732 @@
+//! Resilience of the fleet against a misbehaving chain.
+//!
+//! Each scenario runs the unchanged [`FleetService`] for simulated hours on a
+//! [`VirtualClock`], while a [`FaultPlan`] makes its [`MockProvider`] time
+//! out, go offline or revert. The [`LedgerPlugin`] the wallets execute with
+//! plays the chain's side of their nonces and writes down every transaction
+//! it sends, so each run is checked for what must hold however the chain
+//! misbehaves:
+//!
+//! - circuit breakers trip at the configured error count, reset once their
+//!   cooldown has passed, and a tripped wallet sends nothing
+//! - no wallet executes twice at once, and the nonces it sends with have no
+//!   gaps and no repeats
+//! - the fleet's metrics add up to the requests the mock saw, probes of the
+//!   endpoints included
+
+#![allow(clippy::unwrap_used)]
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, SystemTime};
+
+use alloy::primitives::{Address, Bytes};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use evm_provider::fault::{Fault, FaultPlan, Method};
+use evm_provider::mock::MockProvider;
+use evm_provider::pool::ProviderPool;
+use evm_provider::{ChainProvider, ProviderError};
+use fleet_core::clock::{Clock, SharedClock, VirtualClock};
+use fleet_core::plugins::{
+    Action, ActionId, ActionPlugin, ActionResult, ActionStatus, PendingTx, PluginContext,
+    PluginRegistry,
+};
+use fleet_core::profiles::BehaviorProfile;
+use fleet_core::safety::DegradationTier;
+use fleet_core::validation::ConfigReport;
+use fleet_core::wallet::WalletState;
+
+use crate::config::{
+    BudgetConfig, ChainConfig, ColdStartConfig, ControlConfig, DegradationConfig, DiversityConfig,
+    LeaderConfig, PluginHealthConfig, PluginsConfig, ProfileConfig, ReportConfig, RetirementConfig,
+    ReviewConfig, SafetyConfig, ServiceConfig, Settings, SimulationConfig, TriggersConfig,
+    WalletConfig, WarmupConfig,
+};
+use crate::service::{FleetService, Runtime};
+use crate::signer::Keyring;
+
+/// ID of the [`LedgerPlugin`].
+const PLUGIN_ID: &str = "resilience";
+
+/// The action wallets take most of the time.
+const WORK: &str = "resilience.work";
+
+/// The action scenarios make revert.
+const EXTRACT: &str = "resilience.extract";
+
+/// Consecutive errors that trip a wallet's breaker.
+const MAX_ERRORS: u32 = 3;
+
+/// How long a tripped breaker stays tripped.
+const COOLDOWN: chrono::Duration = chrono::Duration::minutes(30);
+
+/// Seconds between a wallet's actions.
+const ACTION_INTERVAL_SECS: u64 = 300;
+
+/// Smallest step the virtual clock takes, as in the simulation.
+const MIN_STEP: chrono::Duration = chrono::Duration::seconds(1);
+
+/// How long the plugin waits for a receipt.
+const RECEIPT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Virtual start of every scenario.
+fn start() -> DateTime<Utc> {
+    DateTime::from_timestamp(1_767_225_600, 0).unwrap()
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// LEDGER PLUGIN
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// What became of a transaction the plugin tried to send.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SendOutcome {
+    /// Refused for a nonce other than the chain's next one.
+    BadNonce,
+
+    /// The provider failed to take it.
+    Failed,
+
+    /// Accepted, using up its nonce.
+    Accepted,
+}
+
+/// A transaction the plugin tried to send.
+#[derive(Debug, Clone)]
+struct Sent {
+    wallet: String,
+    nonce: u64,
+    at: DateTime<Utc>,
+    outcome: SendOutcome,
+}
+
+/// The chain's view of the wallets, as the plugin keeps it.
+#[derive(Debug, Default)]
+struct Ledger {
+    /// Every transaction the plugin tried to send, in order.
+    sent: Vec<Sent>,
+
+    /// Next nonce the chain accepts per address.
+    next_nonce: HashMap<Address, u64>,
+
+    /// Wallets with an execution in progress.
+    executing: HashSet<String>,
+
+    /// Executions started while the same wallet had one in progress.
+    overlaps: u64,
+
+    /// Replacement transactions built.
+    replacements: u64,
+}
+
+impl Ledger {
+    fn count(&self, outcome: SendOutcome) -> u64 {
+        self.sent.iter().filter(|s| s.outcome == outcome).count() as u64
+    }
+
+    fn sent_by<'a>(&'a self, wallet: &'a str) -> impl Iterator<Item = &'a Sent> {
+        self.sent.iter().filter(move |s| s.wallet == wallet)
+    }
+}
+
+/// Plugin that works and now and then extracts, sending a placeholder
+/// transaction for each action.
+///
+/// The mock chain keeps no nonces of its own, so the plugin refuses any
+/// nonce but the next one with [`ProviderError::NonceTooLow`] and moves the
+/// mock's nonce on once a transaction is accepted.
+#[derive(Debug)]
+struct LedgerPlugin {
+    provider: Arc<MockProvider>,
+    clock: SharedClock,
+
+    /// A wallet's every how manyth action is an extract.
+    extract_every: u64,
+
+    ledger: Mutex<Ledger>,
+}
+
+impl LedgerPlugin {
+    fn ledger(&self) -> MutexGuard<'_, Ledger> {
+        self.ledger.lock().unwrap()
+    }
+
+    async fn send(
+        &self,
+        action: &Action,
+        wallet: &WalletState,
+        nonce: u64,
+    ) -> fleet_core::Result<ActionResult> {
+        let record = |outcome| Sent {
+            wallet: wallet.id.clone(),
+            nonce,
+            at: self.clock.now(),
+            outcome,
+        };
+        let expected = self
+            .ledger()
+            .next_nonce
+            .get(&wallet.address)
+            .copied()
+            .unwrap_or_default();
+        if nonce != expected {
+            self.ledger().sent.push(record(SendOutcome::BadNonce));
+            return Err(ProviderError::NonceTooLow {
+                address: wallet.address,
+                expected,
+                actual: nonce,
+            }
+            .into());
+        }
+
+        let raw = Bytes::from(format!("{}:{}:{nonce}", action.id, wallet.address).into_bytes());
+        let tx_hash = match self.provider.send_raw_transaction(raw).await {
+            Ok(tx_hash) => tx_hash,
+            Err(e) => {
+                self.ledger().sent.push(record(SendOutcome::Failed));
+                return Err(e.into());
+            }
+        };
+        {
+            let mut ledger = self.ledger();
+            ledger.sent.push(record(SendOutcome::Accepted));
+            ledger.next_nonce.insert(wallet.address, nonce + 1);
+        }
+        self.provider.set_nonce(wallet.address, nonce + 1);
+
+        Ok(
+            match self
+                .provider
+                .wait_for_receipt(tx_hash, RECEIPT_TIMEOUT)
+                .await
+            {
+                Ok(receipt) => ActionResult::from_receipt(&receipt),
+                Err(e) => ActionResult::dropped(tx_hash, e.to_string()),
+            },
+        )
+    }
+}
+
+#[async_trait]
+impl ActionPlugin for LedgerPlugin {
+    fn id(&self) -> &'static str {
+        PLUGIN_ID
+    }
+
+    fn name(&self) -> &'static str {
+        "Resilience Ledger"
+    }
+
+    fn available_actions(&self) -> Vec<ActionId> {
+        vec![WORK.into(), EXTRACT.into()]
+    }
+
+    async fn decide_action(
+        &self,
+        wallet: &WalletState,
+        _profile: &BehaviorProfile,
+        _context: &mut PluginContext<'_>,
+    ) -> fleet_core::Result<Option<Action>> {
+        let action = if (wallet.nonce + 1).is_multiple_of(self.extract_every) {
+            Action::new(EXTRACT, "Extract")
+        } else {
+            Action::new(WORK, "Work")
+        };
+        Ok(Some(action))
+    }
+
+    async fn execute_action(
+        &self,
+        action: &Action,
+        wallet: &WalletState,
+        nonce: u64,
+    ) -> fleet_core::Result<ActionResult> {
+        if !self.ledger().executing.insert(wallet.id.clone()) {
+            self.ledger().overlaps += 1;
+        }
+        let result = self.send(action, wallet, nonce).await;
+        self.ledger().executing.remove(&wallet.id);
+        result
+    }
+
+    async fn build_replacement(
+        &self,
+        _action: &Action,
+        original: &PendingTx,
+        bump_pct: u32,
+    ) -> fleet_core::Result<Option<Bytes>> {
+        self.ledger().replacements += 1;
+        let raw = format!("replacement:{}:{bump_pct}", original.nonce);
+        Ok(Some(Bytes::from(raw.into_bytes())))
+    }
+
+    async fn read_state(&self, _address: Address) -> fleet_core::Result<serde_json::Value> {
+        Ok(serde_json::Value::Null)
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// HARNESS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// A wallet's breaker trip as seen between ticks.
+#[derive(Debug, Clone)]
+struct Trip {
+    wallet: String,
+    at: DateTime<Utc>,
+    reset_at: Option<DateTime<Utc>>,
+}
+
+/// A degradation tier the fleet moved to, as seen between ticks.
+#[derive(Debug, Clone)]
+struct TierSeen {
+    at: DateTime<Utc>,
+    tier: DegradationTier,
+
+    /// Consecutive errors each wallet's breaker had counted by then.
+    errors: BTreeMap<String, u32>,
+}
+
+/// A fleet of two wallets on a chain that misbehaves as planned.
+struct Harness {
+    service: FleetService,
+    clock: Arc<VirtualClock>,
+    plan: Arc<FaultPlan>,
+    plugin: Arc<LedgerPlugin>,
+    trips: Vec<Trip>,
+    tiers: Vec<TierSeen>,
+}
+
+impl Harness {
+    /// Fleet whose chain follows `plan`, measured on the fleet's clock, and
+    /// whose wallets extract every `extract_every`th action.
+    fn new(plan: FaultPlan, extract_every: u64) -> Self {
+        Self::with_settings(plan, extract_every, settings())
+    }
+
+    /// Fleet as [`Harness::new`] makes it, but run with `settings`.
+    fn with_settings(plan: FaultPlan, extract_every: u64, settings: Settings) -> Self {
+        let clock = Arc::new(VirtualClock::new(start()));
+        let plan = {
+            let clock = Arc::clone(&clock);
+            Arc::new(plan.with_clock(move || SystemTime::from(clock.now())))
+        };
+        let provider = Arc::new(MockProvider::new());
+        provider.set_fault_plan(Arc::clone(&plan));
+
+        let plugin = Arc::new(LedgerPlugin {
+            provider: Arc::clone(&provider),
+            clock: Arc::clone(&clock) as SharedClock,
+            extract_every,
+            ledger: Mutex::default(),
+        });
+        let mut registry = PluginRegistry::new();
+        registry.register(Arc::clone(&plugin) as Arc<dyn ActionPlugin>).unwrap();
+
+        let signers = Keyring::development(settings.wallets.iter().map(|w| w.id.as_str())).unwrap();
+        let runtime = Runtime {
+            pool: Arc::new(ProviderPool::single(
+                ChainConfig::PRIMARY_ENDPOINT,
+                provider,
+            )),
+            registry,
+            clock: Arc::clone(&clock) as SharedClock,
+            seed: Some(7),
+            signers,
+        };
+
+        Self {
+            service: FleetService::with_runtime(settings, false, runtime),
+            clock,
+            plan,
+            plugin,
+            trips: Vec::new(),
+            tiers: Vec::new(),
+        }
+    }
+
+    /// Run the fleet for `duration` of virtual time, jumping from one
+    /// wakeup to the next like the simulation.
+    async fn run(&mut self, duration: chrono::Duration) {
+        let end = self.clock.now() + duration;
+        loop {
+            self.service.process_tick().await;
+            self.watch_breakers();
+            self.watch_degradation();
+
+            let now = self.clock.now();
+            let Some(next) = self.service.next_wakeup() else {
+                break;
+            };
+            let next = next.max(now + MIN_STEP);
+            if next >= end {
+                break;
+            }
+            self.clock.set(next);
+        }
+    }
+
+    /// Note the breakers that tripped or reset during the last tick.
+    fn watch_breakers(&mut self) {
+        let now = self.clock.now();
+        let breaker = self.service.circuit_breaker();
+        for id in self.service.wallets().keys() {
+            let open = self
+                .trips
+                .iter_mut()
+                .find(|t| t.wallet == *id && t.reset_at.is_none());
+            match (open, breaker.is_tripped(id)) {
+                (None, true) => self.trips.push(Trip {
+                    wallet: id.clone(),
+                    at: now,
+                    reset_at: None,
+                }),
+                (Some(trip), false) => trip.reset_at = Some(now),
+                _ => {}
+            }
+        }
+    }
+
+    /// Note the tier the fleet moved to during the last tick, if any.
+    fn watch_degradation(&mut self) {
+        let tier = self.service.snapshot().degradation.tier;
+        let last = self
+            .tiers
+            .last()
+            .map_or(DegradationTier::Normal, |t| t.tier);
+        if tier == last {
+            return;
+        }
+        let breaker = self.service.circuit_breaker();
+        let errors = self
+            .service
+            .wallets()
+            .keys()
+            .map(|id| (id.clone(), breaker.error_count(id)))
+            .collect();
+        self.tiers.push(TierSeen {
+            at: self.clock.now(),
+            tier,
+            errors,
+        });
+    }
+
+    fn trips_of<'a>(&'a self, wallet: &'a str) -> impl Iterator<Item = &'a Trip> {
+        self.trips.iter().filter(move |t| t.wallet == wallet)
+    }
+
+    /// Check what must hold however the chain misbehaved.
+    fn check_invariants(&self) {
+        let ledger = self.plugin.ledger();
+
+        // Breakers reset promptly once their cooldown passed, and a tripped
+        // wallet sends nothing
+        assert_eq!(
+            self.trips.len() as u64,
+            self.service.circuit_breaker().total_trips()
+        );
+        for trip in &self.trips {
+            if let Some(reset_at) = trip.reset_at {
+                let tripped_for = reset_at - trip.at;
+                assert!(
+                    tripped_for > COOLDOWN && tripped_for <= COOLDOWN + MIN_STEP,
+                    "{trip:?} reset after {tripped_for}"
+                );
+            }
+            let until = trip.reset_at.unwrap_or(DateTime::<Utc>::MAX_UTC);
+            assert!(
+                !ledger
+                    .sent_by(&trip.wallet)
+                    .any(|s| s.at > trip.at && s.at < until),
+                "{trip:?} sent while tripped"
+            );
+        }
+
+        // No wallet executes twice at once, and each sends with the chain's
+        // next nonce, successful or not
+        assert_eq!(ledger.overlaps, 0);
+        for id in self.service.wallets().keys() {
+            let nonces: Vec<_> = ledger
+                .sent_by(id)
+                .filter(|s| s.outcome != SendOutcome::Failed)
+                .map(|s| s.nonce)
+                .collect();
+            assert!(
+                nonces.iter().zip(0..).all(|(nonce, next)| *nonce == next),
+                "{id} sent nonces {nonces:?}"
+            );
+        }
+
+        // Every action either got a transaction in or failed with an error,
+        // and every transaction sent, or probe, reached the mock
+        let snapshot = self.service.snapshot();
+        let errors: u64 = snapshot.errors_by_class.values().sum();
+        assert_eq!(
+            snapshot.total_actions,
+            ledger.count(SendOutcome::Accepted) + errors
+        );
+        assert_eq!(
+            self.plan.calls(Method::SendRawTransaction),
+            ledger.sent.len() as u64 - ledger.count(SendOutcome::BadNonce)
+                + ledger.replacements
+                + snapshot.degradation.probes
+        );
+        drop(ledger);
+    }
+}
+
+fn settings() -> Settings {
+    let wallet = |id: &str, byte: u8| WalletConfig {
+        id: id.into(),
+        address: Address::repeat_byte(byte),
+        profile: "steady".into(),
+        key_source: None,
+        private_key: None,
+        enabled: true,
+        group: None,
+        retiring: false,
+        sweep_to: None,
+        standby: false,
+        budget: BudgetConfig::default(),
+    };
+    let steady = ProfileConfig {
+        action_interval_secs: Some(ACTION_INTERVAL_SECS),
+        active_hours_start: Some(0),
+        active_hours_end: Some(23),
+        afk_probability: Some(0.0),
+        burst_probability: Some(0.0),
+        ..ProfileConfig::default()
+    };
+
+    Settings {
+        service: ServiceConfig::default(),
+        chain: ChainConfig {
+            chain_id: 31337,
+            chain_type: "mock".into(),
+            ..ChainConfig::default()
+        },
+        chains: HashMap::new(),
+        selected_chain: None,
+        wallets: vec![wallet("wallet_1", 1), wallet("wallet_2", 2)],
+        plugins: PluginsConfig {
+            enabled: vec![PLUGIN_ID.into()],
+            // The chain's faults are what the breakers are checked against;
+            // a disabled plugin would hide them
+            health: PluginHealthConfig {
+                auto_disable: false,
+                ..PluginHealthConfig::default()
+            },
+            ..PluginsConfig::default()
+        },
+        safety: SafetyConfig {
+            max_consecutive_errors: MAX_ERRORS,
+            cooldown_secs: COOLDOWN.num_seconds().unsigned_abs(),
+            max_actions_per_hour: 1000,
+            transient_retry_delay_ms: 0,
+            ..SafetyConfig::default()
+        },
+        profiles: HashMap::from([("steady".into(), steady)]),
+        groups: HashMap::new(),
+        mnemonics: HashMap::new(),
+        simulation: SimulationConfig::default(),
+        control: ControlConfig::default(),
+        leader: LeaderConfig::default(),
+        warmup: WarmupConfig::default(),
+        cold_start: ColdStartConfig::default(),
+        diversity: DiversityConfig::default(),
+        review: ReviewConfig::default(),
+        retirement: RetirementConfig::default(),
+        report: ReportConfig::default(),
+        triggers: TriggersConfig::default(),
+        degradation: DegradationConfig::default(),
+        fleets: BTreeMap::new(),
+        load_issues: ConfigReport::new(),
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// SCENARIOS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn flaky_rpc() {
+    let mut harness = Harness::new(FaultPlan::new(7).flaky(0.05, Fault::Timeout), 4);
+    harness.run(chrono::Duration::hours(12)).await;
+    harness.check_invariants();
+
+    // Retries and replacements absorb the timeouts
+    let snapshot = harness.service.snapshot();
+    assert!(harness.plan.total_faults() > 0);
+    assert!(snapshot.total_actions > 200);
+    assert!(snapshot.successful_actions * 100 >= snapshot.total_actions * 95);
+}
+
+#[tokio::test]
+async fn dead_rpc_for_ten_minutes() {
+    let from = start() + chrono::Duration::hours(2);
+    let until = from + chrono::Duration::minutes(10);
+    let plan = FaultPlan::new(7).outage(from.into(), until.into());
+    let mut harness = Harness::new(plan, 4);
+    harness.run(chrono::Duration::hours(6)).await;
+    harness.check_invariants();
+
+    // Nothing is sent while the endpoint is down, and either wallet, due
+    // in the meantime, acts as soon as it is back
+    let ledger = harness.plugin.ledger();
+    assert!(ledger.sent.iter().all(|s| s.at < from || s.at >= until));
+    for id in harness.service.wallets().keys() {
+        let resumed = ledger.sent_by(id).find(|s| s.at >= until).unwrap();
+        assert!(
+            resumed.at <= until + MIN_STEP,
+            "{id} resumed at {}",
+            resumed.at
+        );
+    }
+    drop(ledger);
+
+    // Failed refreshes are not the wallets' fault
+    assert!(harness.plan.faults(Method::Balance) > 0);
+    assert_eq!(harness.service.circuit_breaker().total_trips(), 0);
+    assert!(harness.service.snapshot().errors_by_class.is_empty());
+}
+
+#[tokio::test]
+async fn every_extract_reverts() {
+    let plan = FaultPlan::new(7).revert_matching(|raw| raw.starts_with(EXTRACT.as_bytes()));
+    let mut harness = Harness::new(plan, 1);
+    harness.run(chrono::Duration::hours(6)).await;
+    harness.check_invariants();
+
+    let snapshot = harness.service.snapshot();
+    assert_eq!(snapshot.successful_actions, 0);
+    assert_eq!(
+        snapshot
+            .actions_by_status
+            .get(&ActionStatus::Reverted)
+            .copied(),
+        Some(snapshot.total_actions)
+    );
+
+    // Each wallet trips after MAX_ERRORS reverts in a row, and recovers
+    // only to trip again
+    let ledger = harness.plugin.ledger();
+    for id in harness.service.wallets().keys() {
+        let mut since = start();
+        let mut trips = 0;
+        for trip in harness.trips_of(id) {
+            let reverts = ledger
+                .sent_by(id)
+                .filter(|s| s.at >= since && s.at <= trip.at)
+                .count();
+            assert_eq!(reverts, MAX_ERRORS as usize, "{trip:?}");
+            since = trip.reset_at.unwrap_or(DateTime::<Utc>::MAX_UTC);
+            trips += 1;
+        }
+        assert!(trips > 3, "{id} tripped {trips} times");
+    }
+    drop(ledger);
+}
+
+#[tokio::test]
+async fn alternating_send_failures() {
+    let plan = FaultPlan::new(7).fail_every(Method::SendRawTransaction, 2, Fault::Connection);
+    let mut harness = Harness::new(plan, 4);
+    harness.run(chrono::Duration::hours(6)).await;
+    harness.check_invariants();
+
+    // Every failed send is retried in place and goes through
+    let ledger = harness.plugin.ledger();
+    let failed = ledger.count(SendOutcome::Failed);
+    assert_eq!(harness.plan.faults(Method::SendRawTransaction), failed);
+    assert!(failed.abs_diff(ledger.count(SendOutcome::Accepted)) <= 1);
+    drop(ledger);
+
+    let snapshot = harness.service.snapshot();
+    assert!(snapshot.errors_by_class.is_empty());
+    assert_eq!(snapshot.successful_actions, snapshot.total_actions);
+    assert!(harness.trips.is_empty());
+}
+
+#[tokio::test]
+async fn partial_outage_degrades_and_recovers() {
+    // Sends fail for two hours, and for half an hour of those reads too
+    let sends_from = start() + chrono::Duration::hours(2);
+    let sends_until = sends_from + chrono::Duration::hours(2);
+    let reads_from = sends_from + chrono::Duration::minutes(30);
+    let reads_until = reads_from + chrono::Duration::minutes(30);
+    let plan = FaultPlan::new(7)
+        .outage_of(
+            Method::SendRawTransaction,
+            sends_from.into(),
+            sends_until.into(),
+        )
+        .outage(reads_from.into(), reads_until.into());
+
+    let probe_interval = chrono::Duration::minutes(1);
+    let mut settings = settings();
+    settings.degradation = DegradationConfig {
+        enabled: true,
+        probe_interval_secs: probe_interval.num_seconds().unsigned_abs(),
+        degrade_after_probes: 2,
+        recover_after_probes: 2,
+        ..DegradationConfig::default()
+    };
+    let mut harness = Harness::with_settings(plan, 4, settings);
+    harness.run(chrono::Duration::hours(6)).await;
+    harness.check_invariants();
+
+    // The fleet goes read-only, then pauses and comes back the same way,
+    // each within a few probes of the endpoint changing
+    let tiers: Vec<_> = harness.tiers.iter().map(|t| t.tier).collect();
+    assert_eq!(
+        tiers,
+        [
+            DegradationTier::ReadOnly,
+            DegradationTier::Paused,
+            DegradationTier::ReadOnly,
+            DegradationTier::Normal,
+        ]
+    );
+    let changed_at = [sends_from, reads_from, reads_until, sends_until];
+    for (seen, at) in harness.tiers.iter().zip(changed_at) {
+        assert!(
+            seen.at > at && seen.at <= at + probe_interval * 3,
+            "{seen:?} after the endpoint changed at {at}"
+        );
+    }
+    let (degraded, recovered) = (&harness.tiers[0], &harness.tiers[3]);
+    let status = harness.service.snapshot().degradation;
+    assert_eq!(status.tier, DegradationTier::Normal);
+    assert_eq!(status.transitions, 4);
+
+    // Breakers neither count nor trip while the fleet is degraded
+    for seen in &harness.tiers[1..3] {
+        assert_eq!(seen.errors, degraded.errors, "{seen:?}");
+    }
+    assert!(harness.trips.is_empty());
+    assert!(!harness.service.circuit_breaker().is_frozen());
+
+    // Nothing is sent meanwhile, and either wallet acts again within its
+    // interval once the fleet is back
+    let ledger = harness.plugin.ledger();
+    assert!(
+        ledger
+            .sent
+            .iter()
+            .all(|s| s.at < degraded.at || s.at >= recovered.at)
+    );
+    let interval = chrono::Duration::seconds(ACTION_INTERVAL_SECS.cast_signed());
+    for id in harness.service.wallets().keys() {
+        let resumed = ledger
+            .sent_by(id)
+            .find(|s| s.at >= recovered.at && s.outcome == SendOutcome::Accepted)
+            .unwrap();
+        assert!(
+            resumed.at <= recovered.at + interval * 2,
+            "{id} resumed at {}",
+            resumed.at
+        );
+    }
+    drop(ledger);
+}
@@ -0,0 +1,820 @@
+//! Operator review of planned actions.
+//!
+//! With `review.mode` set, the [`FleetService`](crate::service::FleetService)
+//! runs each tick in two phases. The decide phase collects the actions that
+//! the due wallets decided into a [`PlannedBatch`] instead of running them;
+//! the batch is written to the journal and listed by `ctl plans`. The execute
+//! phase runs the batch on a later tick: in `delay` mode once
+//! `review.delay_secs` have passed, in `strict` mode once it was approved with
+//! `ctl approve`.
+//!
+//! `ctl reject` drops a whole batch, or one wallet's action in it, before it
+//! runs. Rejected actions are not attempted, so they count as neither a
+//! success nor a failure. A wallet with an action under review is not
+//! scheduled again until its batch ran or was rejected.
+//!
+//! # Journal
+//!
+//! With `review.journal` set, each step is appended to the file as one
+//! [`JournalEntry`] per line:
+//!
+//! ```text
+//! {"event":"planned","at":"2025-01-01T12:00:00Z","batch":{"id":1,...}}
+//! {"event":"rejected","at":"...","batch_id":1,"wallet_id":"whale_1"}
+//! {"event":"executed","at":"...","batch_id":1,"wallet_id":"whale_2","status":"succeeded",
+//!  "tx_hash":"0x..."}
+//! ```
+//!
+//! The last entry of a [retired](fleet_core::wallet::Retirement) wallet
+//! records its retirement, whether or not its actions were reviewed:
+//!
+//! ```text
+//! {"event":"retired","at":"...","wallet_id":"whale_1","standby":"whale_9"}
+//! ```
+//!
+//! With `[leader]` enabled, the node that takes over the fleet's lease, and
+//! one that loses it, journal that too:
+//!
+//! ```text
+//! {"event":"lease_acquired","at":"...","node":"fleet-b","term":4}
+//! {"event":"lease_lost","at":"...","node":"fleet-a","term":3}
+//! ```
+//!
+//! With `[degradation]` enabled, the fleet journals the
+//! [tiers](crate::degradation) it moves between, and each action a wallet
+//! decided while read-only but did not execute:
+//!
+//! ```text
+//! {"event":"tier_changed","at":"...","from":"normal","to":"read_only"}
+//! {"event":"withheld","at":"...","wallet_id":"whale_1","plugin_id":"ghostnet",
+//!  "action_id":"ghostnet.jack_in","tier":"read_only"}
+//! ```
+//!
+//! The journal of one of several `[fleet.<name>]` starts each line with
+//! `"fleet":"<name>"`.
+//!
+//! Action data can hold what a transaction encodes into its calldata, so
+//! planned actions are journaled with the `data_hash` of their data and
+//! follow-ups in place of them, and executed ones with the hash of the
+//! transaction they sent. `review.journal_action_data` journals the data
+//! itself.
+
+use std::fmt;
+use std::io::Write;
+use std::path::PathBuf;
+
+use alloy::primitives::{B256, TxHash, keccak256};
+use chrono::{DateTime, Utc};
+use fleet_core::plugins::{Action, ActionId, ActionStatus, FollowUpAction};
+use fleet_core::safety::{DegradationTier, Spend};
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::config::{ReviewConfig, ReviewMode};
+use crate::error::{FleetServiceError, Result};
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// PLANS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// An action a wallet decided, waiting for review.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlannedAction {
+    /// Wallet that acts.
+    pub wallet_id: String,
+
+    /// Plugin that decided the action.
+    pub plugin_id: String,
+
+    /// Action ID, e.g. `ghostnet.jack_in`.
+    pub action_id: ActionId,
+
+    /// Human-readable action name.
+    pub name: String,
+
+    /// Action data the plugin decided.
+    #[serde(default)]
+    pub data: serde_json::Value,
+
+    /// What the plugin expects the action to spend.
+    pub estimate: Spend,
+
+    /// Actions to run after this one.
+    #[serde(default)]
+    pub follow_up: Vec<FollowUpAction>,
+
+    /// Actions of a follow-up chain before this one.
+    #[serde(default)]
+    pub chain_depth: u32,
+
+    /// Rejected with `ctl reject`; the action will not run.
+    #[serde(default)]
+    pub rejected: bool,
+
+    /// Keccak-256 of the JSON array `[data, follow_up]`, journaled in place
+    /// of both unless `review.journal_action_data` is set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data_hash: Option<B256>,
+}
+
+impl PlannedAction {
+    /// Plan `action` of a wallet.
+    #[must_use]
+    pub fn new(wallet_id: &str, plugin_id: &str, action: &Action, estimate: Spend) -> Self {
+        Self {
+            wallet_id: wallet_id.to_string(),
+            plugin_id: plugin_id.to_string(),
+            action_id: action.id.clone(),
+            name: action.name.clone(),
+            data: action.data.clone(),
+            estimate,
+            follow_up: action.follow_up.clone(),
+            chain_depth: action.chain_depth,
+            rejected: false,
+            data_hash: None,
+        }
+    }
+
+    /// The action to execute.
+    #[must_use]
+    pub fn action(&self) -> Action {
+        let mut action =
+            Action::with_data(self.action_id.clone(), self.name.clone(), self.data.clone());
+        action.follow_up.clone_from(&self.follow_up);
+        action.chain_depth = self.chain_depth;
+        action
+    }
+}
+
+/// The actions decided in one tick, executed together once reviewed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlannedBatch {
+    /// Batch ID, counting up from 1 in each run of the fleet.
+    pub id: u64,
+
+    /// When the actions were decided.
+    pub planned_at: DateTime<Utc>,
+
+    /// When the batch runs unless rejected; `None` while it waits for
+    /// approval in strict mode.
+    pub runs_at: Option<DateTime<Utc>>,
+
+    /// Planned actions, in wallet ID order.
+    pub actions: Vec<PlannedAction>,
+}
+
+impl PlannedBatch {
+    /// Actions that were not rejected.
+    pub fn accepted(&self) -> impl Iterator<Item = &PlannedAction> {
+        self.actions.iter().filter(|planned| !planned.rejected)
+    }
+
+    /// The batch with the data and follow-ups of its actions replaced by
+    /// their [`data_hash`](PlannedAction::data_hash), as journaled.
+    #[must_use]
+    pub fn hashed(&self) -> Self {
+        let mut batch = self.clone();
+        for planned in &mut batch.actions {
+            let data = std::mem::take(&mut planned.data);
+            let follow_up = std::mem::take(&mut planned.follow_up);
+            let json = serde_json::json!([data, follow_up]).to_string();
+            planned.data_hash = Some(keccak256(json));
+        }
+        batch
+    }
+}
+
+impl fmt::Display for PlannedBatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Batch {} planned at {}, ", self.id, self.planned_at)?;
+        match self.runs_at {
+            Some(at) => write!(f, "runs at {at}")?,
+            None => write!(f, "awaiting approval")?,
+        }
+        for planned in &self.actions {
+            write!(
+                f,
+                "\n  {:<24} {:<28} gas {} wei, {} DATA wei{}",
+                planned.wallet_id,
+                planned.action_id,
+                planned.estimate.gas_wei,
+                planned.estimate.data,
+                if planned.rejected { " (rejected)" } else { "" }
+            )?;
+        }
+        Ok(())
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// JOURNAL
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// One line of the review journal.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum JournalEntry {
+    /// A batch was planned.
+    Planned {
+        /// When.
+        at: DateTime<Utc>,
+        /// The batch as planned.
+        batch: PlannedBatch,
+    },
+
+    /// A batch was approved.
+    Approved {
+        /// When.
+        at: DateTime<Utc>,
+        /// Approved batch.
+        batch_id: u64,
+    },
+
+    /// A batch, or one wallet's action in it, was rejected.
+    Rejected {
+        /// When.
+        at: DateTime<Utc>,
+        /// Batch of the rejected actions.
+        batch_id: u64,
+        /// Wallet whose action was rejected, `None` for the whole batch.
+        wallet_id: Option<String>,
+    },
+
+    /// A planned action was executed.
+    Executed {
+        /// When.
+        at: DateTime<Utc>,
+        /// Batch of the action.
+        batch_id: u64,
+        /// Wallet that acted.
+        wallet_id: String,
+        /// Outcome of the action.
+        status: ActionStatus,
+        /// Transaction the action sent, if any.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        tx_hash: Option<TxHash>,
+    },
+
+    /// A planned action was not attempted when its batch ran.
+    Skipped {
+        /// When.
+        at: DateTime<Utc>,
+        /// Batch of the action.
+        batch_id: u64,
+        /// Wallet that would have acted.
+        wallet_id: String,
+        /// Why the action was not attempted.
+        reason: String,
+    },
+
+    /// A retiring wallet was retired.
+    Retired {
+        /// When.
+        at: DateTime<Utc>,
+        /// Wallet that was retired.
+        wallet_id: String,
+        /// Standby wallet activated in its place, if any.
+        standby: Option<String>,
+    },
+
+    /// This node took over the fleet's [lease](crate::leader).
+    LeaseAcquired {
+        /// When.
+        at: DateTime<Utc>,
+        /// Node that leads the fleet now.
+        node: String,
+        /// Term of the lease.
+        term: u64,
+    },
+
+    /// This node lost the fleet's lease and stands by.
+    LeaseLost {
+        /// When.
+        at: DateTime<Utc>,
+        /// Node that led the fleet.
+        node: String,
+        /// Term it led in.
+        term: u64,
+    },
+
+    /// The fleet moved to another degradation tier.
+    TierChanged {
+        /// When.
+        at: DateTime<Utc>,
+        /// Tier the fleet was in.
+        from: DegradationTier,
+        /// Tier the fleet is in now.
+        to: DegradationTier,
+    },
+
+    /// A decided action was not executed, as the fleet was degraded.
+    Withheld {
+        /// When.
+        at: DateTime<Utc>,
+        /// Wallet that would have acted.
+        wallet_id: String,
+        /// Plugin that decided the action.
+        plugin_id: String,
+        /// Action that would have run.
+        action_id: ActionId,
+        /// Tier the fleet was in.
+        tier: DegradationTier,
+    },
+}
+
+/// Append-only file of [`JournalEntry`] lines.
+#[derive(Debug)]
+struct Journal {
+    path: PathBuf,
+    /// Fleet the entries are labeled with.
+    fleet: Option<String>,
+    /// Journal the data of planned actions rather than its hash.
+    action_data: bool,
+}
+
+/// A [`JournalEntry`] as written, with the fleet it is of.
+#[derive(Serialize)]
+struct JournalLine<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fleet: Option<&'a str>,
+    #[serde(flatten)]
+    entry: &'a JournalEntry,
+}
+
+impl Journal {
+    /// Append `entry`. A journal that cannot be written is logged, but does
+    /// not hold up the fleet.
+    fn append(&self, entry: &JournalEntry) {
+        let hashed;
+        let entry = match entry {
+            JournalEntry::Planned { at, batch } if !self.action_data => {
+                hashed = JournalEntry::Planned {
+                    at: *at,
+                    batch: batch.hashed(),
+                };
+                &hashed
+            }
+            entry => entry,
+        };
+        let line = JournalLine {
+            fleet: self.fleet.as_deref(),
+            entry,
+        };
+        let written = serde_json::to_string(&line)
+            .map_err(std::io::Error::other)
+            .and_then(|line| {
+                let mut file = std::fs::OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(&self.path)?;
+                writeln!(file, "{line}")
+            });
+        if let Err(e) = written {
+            warn!(path = %self.path.display(), error = %e, "Failed to write review journal");
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// REVIEW QUEUE
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Planned batches waiting for their review.
+#[derive(Debug)]
+pub struct ReviewQueue {
+    /// Review mode.
+    mode: ReviewMode,
+
+    /// How long a batch waits for a rejection in delay mode.
+    delay: chrono::Duration,
+
+    /// Journal of planned batches, if configured.
+    journal: Option<Journal>,
+
+    /// ID of the next batch.
+    next_id: u64,
+
+    /// Actions planned in the current tick, not yet sealed into a batch.
+    drafts: Vec<PlannedAction>,
+
+    /// Batches waiting to run, in ID order.
+    pending: Vec<PlannedBatch>,
+}
+
+impl ReviewQueue {
+    /// Create a queue for the configured review mode.
+    #[must_use]
+    pub fn new(config: &ReviewConfig) -> Self {
+        Self {
+            mode: config.mode,
+            delay: chrono::Duration::seconds(
+                i64::try_from(config.delay_secs).unwrap_or(i64::MAX / 1000),
+            ),
+            journal: config.journal.clone().map(|path| Journal {
+                path,
+                fleet: None,
+                action_data: config.journal_action_data,
+            }),
+            next_id: 1,
+            drafts: Vec::new(),
+            pending: Vec::new(),
+        }
+    }
+
+    /// Label the journal entries with `fleet`.
+    #[must_use]
+    pub fn with_fleet(mut self, fleet: &str) -> Self {
+        if let Some(journal) = &mut self.journal {
+            journal.fleet = Some(fleet.to_string());
+        }
+        self
+    }
+
+    /// Check whether decided actions wait for review.
+    #[must_use]
+    pub fn is_gated(&self) -> bool {
+        self.mode != ReviewMode::Off
+    }
+
+    /// Add an action to the batch of the current tick.
+    pub fn plan(&mut self, planned: PlannedAction) {
+        self.drafts.push(planned);
+    }
+
+    /// Seal the actions planned in the current tick into a batch.
+    ///
+    /// Returns the new batch, or `None` if nothing was planned.
+    pub fn seal(&mut self, now: DateTime<Utc>) -> Option<&PlannedBatch> {
+        if self.drafts.is_empty() {
+            return None;
+        }
+        let mut actions = std::mem::take(&mut self.drafts);
+        actions.sort_by(|a, b| a.wallet_id.cmp(&b.wallet_id));
+        let batch = PlannedBatch {
+            id: self.next_id,
+            planned_at: now,
+            runs_at: (self.mode == ReviewMode::Delay).then(|| now + self.delay),
+            actions,
+        };
+        self.next_id += 1;
+
+        info!(
+            batch = batch.id,
+            actions = batch.actions.len(),
+            runs_at = ?batch.runs_at,
+            "Planned batch for review"
+        );
+        self.journal(&JournalEntry::Planned {
+            at: now,
+            batch: batch.clone(),
+        });
+        self.pending.push(batch);
+        self.pending.last()
+    }
+
+    /// Check whether a wallet has an action under review.
+    #[must_use]
+    pub fn is_pending(&self, wallet_id: &str) -> bool {
+        self.drafts
+            .iter()
+            .any(|planned| planned.wallet_id == wallet_id)
+            || self
+                .pending
+                .iter()
+                .flat_map(PlannedBatch::accepted)
+                .any(|planned| planned.wallet_id == wallet_id)
+    }
+
+    /// Batches waiting to run, in ID order.
+    #[must_use]
+    pub fn batches(&self) -> &[PlannedBatch] {
+        &self.pending
+    }
+
+    /// Approve a batch, so it runs on the next tick.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no such batch is waiting.
+    pub fn approve(&mut self, batch_id: u64, now: DateTime<Utc>) -> Result<&PlannedBatch> {
+        let index = self.index_of(batch_id)?;
+        self.pending[index].runs_at = Some(now);
+        info!(batch = batch_id, "Batch approved");
+        self.journal(&JournalEntry::Approved { at: now, batch_id });
+        Ok(&self.pending[index])
+    }
+
+    /// Reject one wallet's action in a batch, or the whole batch if no
+    /// wallet is given.
+    ///
+    /// A batch with every action rejected is dropped. Returns the number of
+    /// actions rejected.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no such batch is waiting, or the wallet has no
+    /// action in it.
+    pub fn reject(
+        &mut self,
+        batch_id: u64,
+        wallet_id: Option<&str>,
+        now: DateTime<Utc>,
+    ) -> Result<usize> {
+        let index = self.index_of(batch_id)?;
+        let batch = &mut self.pending[index];
+        let mut rejected = 0;
+        for planned in batch.actions.iter_mut().filter(|planned| !planned.rejected) {
+            if wallet_id.is_none_or(|id| id == planned.wallet_id) {
+                planned.rejected = true;
+                rejected += 1;
+            }
+        }
+        if let Some(wallet_id) = wallet_id
+            && rejected == 0
+        {
+            return Err(FleetServiceError::Control(format!(
+                "Wallet {wallet_id} has no action to reject in batch {batch_id}"
+            )));
+        }
+        if batch.accepted().next().is_none() {
+            self.pending.remove(index);
+        }
+
+        info!(
+            batch = batch_id,
+            wallet = ?wallet_id,
+            actions = rejected,
+            "Rejected planned actions"
+        );
+        self.journal(&JournalEntry::Rejected {
+            at: now,
+            batch_id,
+            wallet_id: wallet_id.map(str::to_string),
+        });
+        Ok(rejected)
+    }
+
+    /// Remove the batches due to run at `now`, with their rejected actions
+    /// left out.
+    pub fn take_ready(&mut self, now: DateTime<Utc>) -> Vec<PlannedBatch> {
+        let (ready, waiting) = std::mem::take(&mut self.pending)
+            .into_iter()
+            .partition(|batch| batch.runs_at.is_some_and(|at| at <= now));
+        self.pending = waiting;
+        ready
+            .into_iter()
+            .map(|mut batch: PlannedBatch| {
+                batch.actions.retain(|planned| !planned.rejected);
+                batch
+            })
+            .collect()
+    }
+
+    /// Journal the outcome of a planned action, and the transaction it sent.
+    pub fn record_executed(
+        &self,
+        batch_id: u64,
+        wallet_id: &str,
+        status: ActionStatus,
+        tx_hash: Option<TxHash>,
+        now: DateTime<Utc>,
+    ) {
+        self.journal(&JournalEntry::Executed {
+            at: now,
+            batch_id,
+            wallet_id: wallet_id.to_string(),
+            status,
+            tx_hash,
+        });
+    }
+
+    /// Journal that a planned action was not attempted.
+    pub fn record_skipped(&self, batch_id: u64, wallet_id: &str, reason: &str, now: DateTime<Utc>) {
+        info!(batch = batch_id, wallet = %wallet_id, reason, "Skipped planned action");
+        self.journal(&JournalEntry::Skipped {
+            at: now,
+            batch_id,
+            wallet_id: wallet_id.to_string(),
+            reason: reason.to_string(),
+        });
+    }
+
+    /// Journal that a wallet was retired, and the standby wallet that
+    /// replaces it.
+    pub fn record_retired(&self, wallet_id: &str, standby: Option<&str>, now: DateTime<Utc>) {
+        self.journal(&JournalEntry::Retired {
+            at: now,
+            wallet_id: wallet_id.to_string(),
+            standby: standby.map(str::to_string),
+        });
+    }
+
+    /// Journal that `node` took over the fleet's lease in `term`.
+    pub fn record_lease_acquired(&self, node: &str, term: u64, now: DateTime<Utc>) {
+        self.journal(&JournalEntry::LeaseAcquired {
+            at: now,
+            node: node.to_string(),
+            term,
+        });
+    }
+
+    /// Journal that `node` lost the fleet's lease it held in `term`.
+    pub fn record_lease_lost(&self, node: &str, term: u64, now: DateTime<Utc>) {
+        self.journal(&JournalEntry::LeaseLost {
+            at: now,
+            node: node.to_string(),
+            term,
+        });
+    }
+
+    /// Journal that the fleet moved from tier `from` to `to`.
+    pub fn record_tier_changed(
+        &self,
+        from: DegradationTier,
+        to: DegradationTier,
+        now: DateTime<Utc>,
+    ) {
+        self.journal(&JournalEntry::TierChanged { at: now, from, to });
+    }
+
+    /// Journal that a wallet's action `action_id` of `plugin_id` was not
+    /// executed, as the fleet was in `tier`.
+    pub fn record_withheld(
+        &self,
+        wallet_id: &str,
+        plugin_id: &str,
+        action_id: &ActionId,
+        tier: DegradationTier,
+        now: DateTime<Utc>,
+    ) {
+        self.journal(&JournalEntry::Withheld {
+            at: now,
+            wallet_id: wallet_id.to_string(),
+            plugin_id: plugin_id.to_string(),
+            action_id: action_id.clone(),
+            tier,
+        });
+    }
+
+    /// Position of a waiting batch in `pending`.
+    fn index_of(&self, batch_id: u64) -> Result<usize> {
+        self.pending
+            .iter()
+            .position(|batch| batch.id == batch_id)
+            .ok_or_else(|| FleetServiceError::Control(format!("No planned batch {batch_id}")))
+    }
+
+    fn journal(&self, entry: &JournalEntry) {
+        if let Some(journal) = &self.journal {
+            journal.append(entry);
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn planned(wallet_id: &str) -> PlannedAction {
+        let action = Action::new("ghostnet.jack_in", "Jack In");
+        PlannedAction::new(wallet_id, "ghostnet", &action, Spend::action())
+    }
+
+    #[test]
+    fn journal_records_each_step() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("plans.jsonl");
+        let config = ReviewConfig {
+            mode: ReviewMode::Strict,
+            journal: Some(path.clone()),
+            ..ReviewConfig::default()
+        };
+        let mut queue = ReviewQueue::new(&config);
+        let now = Utc.with_ymd_and_hms(2025, 1, 1, 12, 0, 0).unwrap();
+
+        queue.plan(planned("w2"));
+        queue.plan(planned("w1"));
+        let batch = queue.seal(now).unwrap().clone();
+        assert_eq!(batch.runs_at, None);
+        assert_eq!(batch.actions[0].wallet_id, "w1");
+        assert!(queue.seal(now).is_none());
+
+        assert_eq!(queue.reject(1, Some("w1"), now).unwrap(), 1);
+        queue.approve(1, now).unwrap();
+        let tx_hash = TxHash::repeat_byte(0x42);
+        queue.record_executed(1, "w2", ActionStatus::Succeeded, Some(tx_hash), now);
+
+        let journal: Vec<JournalEntry> = std::fs::read_to_string(&path)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(
+            journal,
+            [
+                JournalEntry::Planned {
+                    at: now,
+                    batch: batch.hashed()
+                },
+                JournalEntry::Rejected {
+                    at: now,
+                    batch_id: 1,
+                    wallet_id: Some("w1".into()),
+                },
+                JournalEntry::Approved {
+                    at: now,
+                    batch_id: 1
+                },
+                JournalEntry::Executed {
+                    at: now,
+                    batch_id: 1,
+                    wallet_id: "w2".into(),
+                    status: ActionStatus::Succeeded,
+                    tx_hash: Some(tx_hash),
+                },
+            ]
+        );
+        assert!(
+            std::fs::read_to_string(&path)
+                .unwrap()
+                .starts_with(r#"{"event":"planned","#)
+        );
+    }
+
+    #[test]
+    fn journal_hashes_action_data_unless_asked_for_it() {
+        let key = "59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d";
+        let action = Action::with_data(
+            "ghostnet.jack_in",
+            "Jack In",
+            serde_json::json!({ "amount": "1000", "memo": key }),
+        );
+        let journal = |action_data: bool| {
+            let dir = tempfile::tempdir().unwrap();
+            let path = dir.path().join("plans.jsonl");
+            let config = ReviewConfig {
+                mode: ReviewMode::Strict,
+                journal: Some(path.clone()),
+                journal_action_data: action_data,
+                ..ReviewConfig::default()
+            };
+            let mut queue = ReviewQueue::new(&config);
+            queue.plan(PlannedAction::new(
+                "w1",
+                "ghostnet",
+                &action,
+                Spend::action(),
+            ));
+            let batch = queue.seal(Utc::now()).unwrap().clone();
+            (batch, std::fs::read_to_string(&path).unwrap())
+        };
+
+        let (batch, hashed) = journal(false);
+        assert!(!hashed.contains(key), "{hashed}");
+        let line: serde_json::Value = serde_json::from_str(&hashed).unwrap();
+        let planned: PlannedAction =
+            serde_json::from_value(line["batch"]["actions"][0].clone()).unwrap();
+        assert_eq!(planned.data, serde_json::Value::Null);
+        let json = serde_json::json!([action.data, action.follow_up]).to_string();
+        assert_eq!(planned.data_hash, Some(keccak256(json)));
+        assert_eq!(batch.actions[0].data, action.data);
+
+        let (_, full) = journal(true);
+        assert!(full.contains(key), "{full}");
+        assert!(!full.contains("data_hash"), "{full}");
+    }
+
+    #[test]
+    fn journal_lines_name_the_fleet() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("plans.jsonl");
+        let config = ReviewConfig {
+            mode: ReviewMode::Strict,
+            journal: Some(path.clone()),
+            ..ReviewConfig::default()
+        };
+        let mut queue = ReviewQueue::new(&config).with_fleet("alpha");
+        let now = Utc.with_ymd_and_hms(2025, 1, 1, 12, 0, 0).unwrap();
+        queue.plan(planned("w1"));
+        queue.seal(now).unwrap();
+        queue.approve(1, now).unwrap();
+
+        let lines = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<_> = lines.lines().collect();
+        assert!(lines[0].starts_with(r#"{"fleet":"alpha","event":"planned","#));
+        assert_eq!(
+            serde_json::from_str::<JournalEntry>(lines[1]).unwrap(),
+            JournalEntry::Approved {
+                at: now,
+                batch_id: 1
+            }
+        );
+    }
+}
@@ -3,41 +3,90 @@
 //! The [`FleetService`] is the core orchestrator that ties together:
 //! - Wallet management and state tracking
 //! - Plugin registration and action coordination
-//! - Safety mechanisms (circuit breakers, rate limiting)
+//! - Safety mechanisms (circuit breakers, rate limiting, wallet group limits,
+//!   spend budgets)
+//! - Error handling by [`ErrorClass`]: in-place retries, back-off and breakers
 //! - Scheduling with profile-based timing
+//! - Action metrics from structured [`ActionResult`]s
+//! - Optional [review](crate::review) of decided actions before they run
+//! - Retirement of wallets that are wound down, and standby wallets that
+//!   replace them
+//! - Daily [activity reports](crate::report) of the wallets
+//! - A [profit and loss ledger](crate::pnl) of the DATA the wallets moved
+//!   and the gas they paid
+//! - A cold-start ramp that spreads the wallets overdue at startup
+//! - Fleet-level [composition](fleet_core::profiles::FleetComposer) of the
+//!   wallets' profiles, and a warning when they act in lockstep anyway
+//! - A [state file](crate::state) that carries the wallets' schedules and
+//!   the circuit breaker over restarts
+//! - Active-passive [leadership](crate::leader) among several processes of
+//!   the same fleet
+//!
+//! All timing reads the time from a [`Clock`](fleet_core::clock::Clock), so
+//! the same service can run live or be driven through virtual time by the
+//! [simulation](crate::simulation).
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
+use alloy::primitives::{Address, U256};
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use evm_provider::mock::MockProvider;
+use evm_provider::pool::ProviderPool;
 use evm_provider::ChainProvider;
-use fleet_core::plugins::PluginRegistry;
-use fleet_core::profiles::BehaviorProfile;
-use fleet_core::safety::CircuitBreaker;
-use fleet_core::scheduler::Scheduler;
-use fleet_core::wallet::WalletState;
-use ghostnet_actions::{GhostnetConfig, GhostnetPlugin};
-use tokio::sync::watch;
+use fleet_core::clock::{SharedClock, system_clock};
+use fleet_core::metrics::{FleetMetrics, FleetSnapshot};
+use fleet_core::plugins::{
+    Action, ActionCooldowns, ActionError, ActionId, ActionPlugin, ActionResult, ActionStatus,
+    MAX_CHAIN_DEPTH, PendingFollowUp, PluginRegistry,
+};
+use fleet_core::profiles::{
+    BehaviorProfile, Composition, FleetComposer, ProfileCatalog, personality_seed,
+};
+use fleet_core::safety::{BudgetManager, CircuitBreaker, DegradationTier, GlobalBreaker, Spend};
+use fleet_core::{ErrorClass, FleetError};
+use fleet_core::scheduler::{ColdStartRamp, GroupLimiter, OverdueWallet, Scheduler};
+use fleet_core::wallet::{BalanceRefresher, WalletState, WarmupSettings};
+use ghostnet_actions::GhostnetPlugin;
+use tokio::sync::{mpsc, watch};
 use tokio::time::interval;
 use tracing::{debug, error, info, instrument, warn};
 
-use crate::config::Settings;
-use crate::engine::BehaviorEngine;
+use crate::config::{ChainConfig, PluginTimeoutConfig, SafetyConfig, Settings, WalletConfig};
+use crate::control::{
+    ControlCommand, ControlHandle, ControlResponse, Envelope, FleetStatus, PluginSwitch,
+    WalletStatus,
+};
+use crate::degradation::DegradationMonitor;
+use crate::engine::{
+    ACTION_SWEEP, ActionTimeouts, BehaviorEngine, ENGINE_ID, ReplacementPolicy, RetryPolicy,
+    SweepAsset,
+};
+use crate::error::FleetServiceError;
+use crate::leader::{FileLease, Leadership};
+use crate::pnl::{PnlEntry, PnlLedger};
+use crate::report::{Activity, ReportGenerator, ReportSnapshot, WalletReading};
+use crate::review::{PlannedAction, PlannedBatch, ReviewQueue};
+use crate::signer::Keyring;
+use crate::state::{self, FleetState};
+use crate::trigger::{self, EventTrigger, ProtocolEvent};
 
 // ═══════════════════════════════════════════════════════════════════════════════
 // RATE LIMITER
 // ═══════════════════════════════════════════════════════════════════════════════
 
 /// Tracks action counts per wallet for rate limiting.
-#[derive(Debug, Default)]
+#[derive(Debug)]
 struct RateLimiter {
     /// Action timestamps per wallet (for sliding window).
     action_times: HashMap<String, Vec<DateTime<Utc>>>,
     /// Maximum actions per hour.
     max_per_hour: u32,
+    /// Source of the current time.
+    clock: SharedClock,
 }
 
 impl RateLimiter {
@@ -46,16 +95,23 @@ impl RateLimiter {
         Self {
             action_times: HashMap::new(),
             max_per_hour,
+            clock: system_clock(),
         }
     }
 
+    /// Use `clock` instead of the system clock.
+    fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
     /// Check if the wallet would exceed the rate limit.
     fn would_exceed(&self, wallet_id: &str) -> bool {
         let Some(times) = self.action_times.get(wallet_id) else {
             return false;
         };
 
-        let one_hour_ago = Utc::now() - chrono::Duration::hours(1);
+        let one_hour_ago = self.clock.now() - chrono::Duration::hours(1);
         let recent_count = times.iter().filter(|t| **t > one_hour_ago).count();
 
         recent_count >= self.max_per_hour as usize
@@ -63,11 +119,12 @@ impl RateLimiter {
 
     /// Record an action for rate limiting.
     fn record_action(&mut self, wallet_id: &str) {
+        let now = self.clock.now();
         let times = self.action_times.entry(wallet_id.to_string()).or_default();
-        times.push(Utc::now());
+        times.push(now);
 
         // Prune old entries (older than 1 hour)
-        let one_hour_ago = Utc::now() - chrono::Duration::hours(1);
+        let one_hour_ago = now - chrono::Duration::hours(1);
         times.retain(|t| *t > one_hour_ago);
     }
 
@@ -78,11 +135,74 @@ impl RateLimiter {
             return 0;
         };
 
-        let one_hour_ago = Utc::now() - chrono::Duration::hours(1);
+        let one_hour_ago = self.clock.now() - chrono::Duration::hours(1);
         times.iter().filter(|t| **t > one_hour_ago).count()
     }
 }
 
+/// Outcome of [`FleetService::execute_action`].
+#[derive(Debug)]
+#[allow(clippy::large_enum_variant)] // Nearly every execution is done; boxing would allocate each
+enum Execution {
+    /// The action was attempted and its result recorded.
+    Done {
+        /// Recorded result.
+        result: ActionResult,
+        /// Earliest time the wallet may act again, if it has to back off.
+        not_before: Option<DateTime<Utc>>,
+    },
+
+    /// The action was not attempted because the budget cannot cover it.
+    OverBudget {
+        /// When the budget allows spending again, if ever.
+        resumes_at: Option<DateTime<Utc>>,
+    },
+
+    /// The action was not attempted because this node lost the fleet's
+    /// lease before sending it.
+    Aborted,
+}
+
+impl Execution {
+    /// Earliest time the wallet may act again.
+    const fn not_before(&self) -> Option<DateTime<Utc>> {
+        match self {
+            Self::Done { not_before, .. } => *not_before,
+            Self::OverBudget { resumes_at } => *resumes_at,
+            Self::Aborted => None,
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// RUNTIME
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// RPC endpoints of the fleet's chain, with wallets assigned to them.
+///
+/// TODO: Pool `Arc<dyn ChainProvider>` once we have real provider implementations.
+pub type FleetPool = ProviderPool<Arc<MockProvider>>;
+
+/// Dependencies of a [`FleetService`] that live runs and simulations provide
+/// differently.
+#[derive(Debug)]
+pub struct Runtime {
+    /// RPC endpoints the wallets' requests are spread over.
+    pub pool: Arc<FleetPool>,
+
+    /// Registered plugins.
+    pub registry: PluginRegistry,
+
+    /// Source of the current time.
+    pub clock: SharedClock,
+
+    /// Seed for every random number generator, or `None` to seed from the OS.
+    pub seed: Option<u64>,
+
+    /// Signing keys of the wallets.
+    pub signers: Keyring,
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // FLEET SERVICE
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -96,7 +216,7 @@ impl RateLimiter {
 ///
 /// The service runs a tick-based loop:
 /// 1. Check shutdown signal
-/// 2. Check global pause flag
+/// 2. Check global pause flag and global breaker
 /// 3. Check circuit breaker auto-reset
 /// 4. Get wallets due for action
 /// 5. For each due wallet:
@@ -104,15 +224,86 @@ impl RateLimiter {
 ///    b. Refresh state from chain
 ///    c. Check circuit breaker
 ///    d. Consult plugins for action decision
-///    e. Execute action if decided
+///    e. Execute action if decided and within the wallet's and fleet's budgets
 ///    f. Schedule next action
 ///
+/// With [review](crate::review) enabled, step 5e plans the action instead,
+/// and the tick's planned actions form a batch. Batches that were approved,
+/// or waited out their review delay, run at the start of a later tick, after
+/// step 3.
+///
+/// # Follow-ups
+///
+/// Once an action's result is recorded, the [follow-ups](fleet_core::plugins::follow_up)
+/// that match its outcome are queued on the wallet, which becomes due when
+/// the first of them is. A due follow-up takes the place of step 5d and goes
+/// through the same breakers, limits, budgets and cooldowns as a decided
+/// action; one still on cooldown or over budget waits until it may run.
+///
+/// # Execution Errors
+///
+/// An action that fails with an error is handled by its [`ErrorClass`]:
+/// transient errors are retried in place by the engine and count toward the
+/// wallet's circuit breaker only once the retries are used up; rate-limited
+/// errors push the wallet's next action out and feed the [`GlobalBreaker`];
+/// permanent errors count toward the circuit breaker right away;
+/// configuration errors are only logged.
+///
+/// # Cold Start
+///
+/// Wallets whose next action passed while the fleet was down would all be
+/// due on the first tick. Unless `[cold_start]` is disabled, the first tick
+/// reschedules them over the ramp window instead (see [`ColdStartRamp`]),
+/// and meters the state reads of their first cycle; breakers and limits
+/// apply to them as usual.
+///
+/// # Retirement
+///
+/// A [retiring](fleet_core::wallet::Retirement) wallet only runs exit
+/// actions. Once no plugin holds anything open for it, it sweeps one balance
+/// above `[retirement]` dust per turn to its sweep address, tokens first; with
+/// only dust left it is retired, journaled, and replaced by the first
+/// standby wallet left, which takes over its behavior profile.
+///
+/// # Event Triggers
+///
+/// With `[triggers]` enabled, the protocol events received since the last
+/// tick move the next action of the wallets they concern earlier, after
+/// step 3 (see [`crate::trigger`]); events received while the fleet is
+/// paused, its global breaker is open or this node stands by are dropped.
+/// A woken wallet goes through step 5 as usual, except that outside its
+/// active hours it may run the `urgent_actions` that are exits, and nothing
+/// else.
+///
+/// # Degradation
+///
+/// With `[degradation]` enabled, each tick first probes the RPC endpoints
+/// when a probe is due, and the fleet moves between tiers as the probes
+/// find them (see [`crate::degradation`]), taking in what became of the
+/// actions executed since the last probe. While the realtime API fails,
+/// transactions are submitted the standard way. While sends fail, wallets go
+/// through step 5 as usual, but each action decided in step 5e is journaled
+/// and logged as withheld instead of executed, and reviewed batches wait.
+/// While reads fail too, the tick ends after the probe. Circuit breakers are
+/// frozen and the global breaker stays closed meanwhile; once the fleet is
+/// back from paused, its overdue wallets ramp up as after a cold start.
+///
+/// # Leadership
+///
+/// With `[leader]` enabled, several processes run the same fleet but only
+/// the one holding its [lease](crate::leader) acts; the others stand by,
+/// following the state file. Each heartbeat renews the lease. A standby that
+/// takes over an expired lease loads the state file, forgets what it knew
+/// of the wallets' nonces, and ramps up as after a cold start. The lease is
+/// confirmed again right before each transaction is sent, so a node that
+/// lost it in the meantime sends nothing.
+///
 /// # Example
 ///
 /// ```ignore
 /// let settings = Settings::load("config.toml")?;
 /// let (shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(false);
-/// let service = FleetService::new(settings, false).await?;
+/// let service = FleetService::new(settings, false, Keyring::new()).await?;
 ///
 /// // Run in a task
 /// let handle = tokio::spawn(async move {
@@ -128,11 +319,10 @@ pub struct FleetService {
     /// Configuration settings.
     settings: Settings,
 
-    /// Chain provider for blockchain interactions.
+    /// RPC endpoints the wallets' requests are spread over.
     ///
-    /// TODO: Make this `Arc<dyn ChainProvider>` once we have real provider implementations.
-    /// Currently uses MockProvider directly since GhostnetPlugin<P> requires a concrete type.
-    provider: Arc<MockProvider>,
+    /// Currently pools MockProviders directly since GhostnetPlugin<P> requires a concrete type.
+    pool: Arc<FleetPool>,
 
     /// Plugin registry.
     #[expect(dead_code, reason = "Stored for future plugin hot-reload")]
@@ -144,20 +334,103 @@ pub struct FleetService {
     /// Circuit breaker for error handling.
     circuit_breaker: CircuitBreaker,
 
+    /// Fleet-wide breaker fed by rate-limited errors.
+    global_breaker: GlobalBreaker,
+
+    /// Daily and lifetime spend caps of the wallets and the fleet.
+    budget: BudgetManager,
+
     /// Rate limiter for action throttling.
     rate_limiter: RateLimiter,
 
     /// Scheduler for timing calculations.
     scheduler: Scheduler,
 
+    /// Action limits of wallet groups.
+    group_limiter: GroupLimiter,
+
     /// Wallet states by wallet ID.
     wallets: HashMap<String, WalletState>,
 
+    /// Signing keys by wallet ID.
+    signers: Keyring,
+
     /// Behavior profiles by name.
-    profiles: HashMap<String, BehaviorProfile>,
+    profiles: ProfileCatalog,
+
+    /// Profiles of the wallets as composed at startup, see
+    /// [`compose_profiles`](Self::compose_profiles).
+    composition: Composition,
+
+    /// Action outcome metrics, shared with the engine.
+    metrics: Arc<FleetMetrics>,
+
+    /// Source of the current time.
+    clock: SharedClock,
 
     /// Dry run mode (no transactions sent).
     dry_run: bool,
+
+    /// Commands from the control socket, see [`control`](Self::control).
+    control: Option<mpsc::Receiver<Envelope>>,
+
+    /// Config file the profiles are reloaded from.
+    config_path: Option<PathBuf>,
+
+    /// Wallet and plugin IDs whose state was last taken from a receipt, so
+    /// the next refresh need not read it.
+    receipt_states: HashSet<(String, String)>,
+
+    /// Lowest nonce each wallet that moved to another endpoint may have,
+    /// until the new endpoint caught up with it.
+    nonce_floors: HashMap<String, u64>,
+
+    /// Planned batches waiting for review.
+    review: ReviewQueue,
+
+    /// Daily activity reports.
+    reports: ReportGenerator,
+
+    /// DATA flows and gas of the wallets.
+    pnl: PnlLedger,
+
+    /// Name of this fleet among several, see [`Fleets`](crate::fleets::Fleets).
+    fleet_id: Option<String>,
+
+    /// Standby wallets not yet activated, in config order.
+    standby: Vec<WalletConfig>,
+
+    /// Seed of the randomness, for wallets activated later.
+    seed: Option<u64>,
+
+    /// Whether the first tick has run and planned the cold-start ramp.
+    started: bool,
+
+    /// Ramp-up of the wallets overdue at startup, while it runs.
+    cold_start: Option<ColdStartRamp>,
+
+    /// Lease this node acts under, with `[leader]` enabled.
+    leadership: Option<Arc<Leadership>>,
+
+    /// Term of the lease this node last took over, while it leads.
+    leading: Option<u64>,
+
+    /// Whether the last correlation check flagged the fleet.
+    correlated: bool,
+
+    /// Picks the wallets protocol events wake, with `[triggers]` enabled.
+    trigger: Option<EventTrigger>,
+
+    /// Protocol events of the event source, see
+    /// [`watch_events`](Self::watch_events).
+    events: Option<mpsc::Receiver<ProtocolEvent>>,
+
+    /// Wallets woken by a protocol event and not processed since.
+    woken: HashSet<String>,
+
+    /// Probes of the RPC endpoints and the tier they put the fleet in, with
+    /// `[degradation]` enabled.
+    degradation: Option<DegradationMonitor>,
 }
 
 impl FleetService {
@@ -167,75 +440,229 @@ impl FleetService {
     ///
     /// * `settings` - Configuration settings
     /// * `dry_run` - If true, actions are logged but not executed
+    /// * `signers` - Signing keys loaded at startup
     ///
     /// # Errors
     ///
     /// Returns an error if provider initialization fails.
     #[expect(clippy::unused_async, reason = "async for future provider initialization")]
-    pub async fn new(settings: Settings, dry_run: bool) -> Result<Self> {
+    pub async fn new(settings: Settings, dry_run: bool, signers: Keyring) -> Result<Self> {
         info!(
+            chain = %settings.chain_key(),
             chain_type = %settings.chain.chain_type,
             chain_id = settings.chain.chain_id,
             dry_run = dry_run,
             "Initializing Fleet Service"
         );
 
-        // Create provider based on chain type
-        let provider = Self::create_provider(&settings)?;
+        // Create providers based on chain type, and make sure they serve the
+        // configured chain before anything is sent to them
+        let pool = Self::create_pool(&settings)?;
+        settings.check_chain_id(pool.primary().chain_id())?;
 
         // Initialize plugin registry
-        let registry = Self::create_registry(&settings, Arc::clone(&provider));
+        let registry = Self::create_registry(&settings, Arc::clone(pool.primary().inner()))?;
 
-        // Create behavior engine
-        let engine = BehaviorEngine::new(&registry, &settings.plugins.enabled);
+        let runtime = Runtime {
+            pool,
+            registry,
+            clock: system_clock(),
+            seed: None,
+            signers,
+        };
+        Ok(Self::with_runtime(settings, dry_run, runtime))
+    }
 
-        // Create circuit breaker
-        let circuit_breaker = CircuitBreaker::new(
-            settings.safety.max_consecutive_errors,
-            Duration::from_secs(settings.safety.cooldown_secs),
+    /// Create a fleet service on the given runtime dependencies.
+    ///
+    /// Used by [`new`](Self::new) for live runs and by the simulation to run
+    /// on a virtual clock with seeded randomness.
+    pub fn with_runtime(settings: Settings, dry_run: bool, runtime: Runtime) -> Self {
+        let Runtime {
+            pool,
+            registry,
+            clock,
+            seed,
+            signers,
+        } = runtime;
+
+        // Create behavior engine; a seeded run gives each RNG its own stream
+        let metrics = Arc::new(
+            FleetMetrics::new()
+                .with_clock(Arc::clone(&clock))
+                .with_correlation(settings.diversity.to_correlation_settings()),
+        );
+        let leadership = Self::create_leadership(&settings, &clock);
+        let engine = Self::create_engine(
+            &settings,
+            &registry,
+            &clock,
+            seed,
+            &metrics,
+            leadership.as_ref(),
         );
 
+        // Create circuit breakers
+        let (circuit_breaker, global_breaker) = Self::create_breakers(&settings, &clock);
+
         // Create rate limiter
-        let rate_limiter = RateLimiter::new(settings.safety.max_actions_per_hour);
+        let rate_limiter = RateLimiter::new(settings.safety.max_actions_per_hour)
+            .with_clock(Arc::clone(&clock));
 
-        // Create scheduler
-        let scheduler = Scheduler::new();
+        // Create group limiter
+        let group_limiter = Self::create_group_limiter(&settings);
 
         // Initialize wallet states
-        let wallets = Self::initialize_wallets(&settings);
+        let wallets = Self::initialize_wallets(&settings, clock.now(), seed);
+
+        // Create scheduler, with a jitter sequence of each wallet's own
+        let mut scheduler = seed
+            .map_or_else(Scheduler::new, Scheduler::with_seed)
+            .with_clock(Arc::clone(&clock));
+        for wallet in wallets.values() {
+            scheduler.seed_wallet(wallet.id.clone(), Self::jitter_seed(wallet, seed));
+        }
+
+        // Create budget manager, carrying over what the wallets already spent
+        let budget = Self::create_budget(&settings, &wallets, Arc::clone(&clock));
 
-        // Load behavior profiles
+        // Load behavior profiles, and compose the wallets' into a diverse fleet
         let profiles = Self::load_profiles(&settings);
+        let composition = Self::compose_profiles(&settings, &profiles, &wallets);
+
+        let review = ReviewQueue::new(&settings.review);
+        let reports = ReportGenerator::new(&settings.report, ReportSnapshot::empty(clock.now()));
+
+        // Draws of event wakeups get a stream of their own too
+        let trigger = settings.triggers.enabled.then(|| {
+            EventTrigger::new(
+                &settings.triggers,
+                Arc::clone(&clock),
+                seed.map(|seed| seed ^ 2),
+            )
+        });
+
+        let degradation = settings
+            .degradation
+            .enabled
+            .then(|| DegradationMonitor::new(&settings.degradation, clock.now()));
+
+        let standby = Self::standby_wallets(&settings);
 
         info!(
             wallets = wallets.len(),
+            signers = signers.len(),
             profiles = profiles.len(),
             plugins = settings.plugins.enabled.len(),
+            groups = settings.groups.len(),
+            standby = standby.len(),
             max_actions_per_hour = settings.safety.max_actions_per_hour,
             "Fleet Service initialized"
         );
 
-        Ok(Self {
+        let mut service = Self {
             settings,
-            provider,
+            pool,
             registry,
             engine,
             circuit_breaker,
+            global_breaker,
+            budget,
             rate_limiter,
             scheduler,
+            group_limiter,
             wallets,
+            signers,
             profiles,
+            composition,
+            metrics,
+            clock,
             dry_run,
-        })
+            control: None,
+            config_path: None,
+            receipt_states: HashSet::new(),
+            nonce_floors: HashMap::new(),
+            review,
+            reports,
+            pnl: PnlLedger::new(),
+            fleet_id: None,
+            standby,
+            seed,
+            started: false,
+            cold_start: None,
+            leadership,
+            leading: None,
+            correlated: false,
+            trigger,
+            events: None,
+            woken: HashSet::new(),
+            degradation,
+        };
+        // The first report starts from what the wallets did before
+        service.reports.rebase(service.report_snapshot());
+        service
+    }
+
+    /// Name this fleet: its snapshots and review journal carry `id`.
+    #[must_use]
+    pub fn with_fleet_id(mut self, id: impl Into<String>) -> Self {
+        let id = id.into();
+        self.review = self.review.with_fleet(&id);
+        self.reports = self.reports.with_fleet(&id);
+        self.fleet_id = Some(id);
+        self
+    }
+
+    /// Name of this fleet among several, if it has one.
+    #[must_use]
+    pub fn fleet_id(&self) -> Option<&str> {
+        self.fleet_id.as_deref()
+    }
+
+    /// Reload profiles from `path` on [`ControlCommand::ReloadProfiles`].
+    #[must_use]
+    pub fn with_config_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.config_path = Some(path.into());
+        self
+    }
+
+    /// Create a handle that sends commands to the service once it
+    /// [runs](Self::run).
+    ///
+    /// Commands are handled on the main loop, between ticks. A new handle
+    /// replaces any earlier one.
+    pub fn control(&mut self) -> ControlHandle {
+        let (handle, receiver) = ControlHandle::channel();
+        self.control = Some(receiver);
+        handle
+    }
+
+    /// Take protocol events from the source `[triggers]` configures until
+    /// `shutdown`, see [`crate::trigger`].
+    ///
+    /// Does nothing unless triggers are enabled. The events are handled on
+    /// the main loop, at the start of each tick.
+    pub fn watch_events(&mut self, shutdown: watch::Receiver<bool>) {
+        if self.trigger.is_none() {
+            return;
+        }
+        let provider = Arc::clone(self.pool.primary());
+        let ghost_core = self.settings.chain.ghostnet.ghost_core;
+        self.events = Some(trigger::spawn_source(
+            &self.settings.triggers,
+            ghost_core,
+            provider,
+            shutdown,
+        ));
     }
 
-    /// Create the chain provider based on settings.
+    /// Create the chain provider of one RPC endpoint based on settings.
     ///
     /// TODO: Return `Arc<dyn ChainProvider>` once we have real provider implementations.
-    fn create_provider(settings: &Settings) -> Result<Arc<MockProvider>> {
+    fn create_provider(settings: &Settings, url: &str) -> Result<Arc<MockProvider>> {
         match settings.chain.chain_type.as_str() {
             "mock" => {
-                info!("Using mock provider for testing");
+                debug!(url, "Using mock provider for testing");
                 Ok(Arc::new(MockProvider::with_chain_id(settings.chain.chain_id)))
             }
             "standard" | "megaeth" => {
@@ -243,6 +670,7 @@ impl FleetService {
                 // TODO: Implement real providers (StandardEvmProvider, MegaEthProvider)
                 warn!(
                     chain_type = %settings.chain.chain_type,
+                    url,
                     "Real provider not yet implemented, using mock"
                 );
                 Ok(Arc::new(MockProvider::with_chain_id(settings.chain.chain_id)))
@@ -253,64 +681,484 @@ impl FleetService {
         }
     }
 
+    /// Create the pool of `rpc_url` and the chain's extra endpoints.
+    pub(crate) fn create_pool(settings: &Settings) -> Result<Arc<FleetPool>> {
+        let chain = &settings.chain;
+        let mut endpoints = vec![(
+            ChainConfig::PRIMARY_ENDPOINT.to_string(),
+            1,
+            Self::create_provider(settings, &chain.rpc_url)?,
+        )];
+        for endpoint in &chain.endpoints {
+            let provider = Self::create_provider(settings, &endpoint.url)?;
+            endpoints.push((endpoint.name.clone(), endpoint.weight, provider));
+        }
+
+        info!(
+            endpoints = endpoints.len(),
+            assignment = ?chain.endpoint_assignment,
+            "RPC endpoints configured"
+        );
+        let pool = ProviderPool::new(chain.endpoint_assignment, endpoints)
+            .context("Invalid RPC endpoints")?
+            .with_failure_threshold(chain.endpoint_failure_threshold)
+            .with_retry_after(Duration::from_secs(chain.endpoint_retry_secs));
+        Ok(Arc::new(pool))
+    }
+
     /// Create and populate the plugin registry.
-    fn create_registry(
+    ///
+    /// Plugins read through a single `provider`, the pool's primary endpoint
+    /// in live runs, rather than through each wallet's endpoint.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a plugin lists malformed action IDs.
+    pub fn create_registry(
         settings: &Settings,
         provider: Arc<MockProvider>,
-    ) -> PluginRegistry {
+    ) -> Result<PluginRegistry> {
         let mut registry = PluginRegistry::new();
 
         // Register GHOSTNET plugin if enabled
         // Use iter().any() to avoid string allocation, and combine conditions
         if settings.plugins.enabled.iter().any(|s| s == "ghostnet")
-            && let Some(ghostnet_config) = &settings.plugins.ghostnet
+            && let Some(config) = settings.ghostnet_config()
         {
-            let config = GhostnetConfig::new(
-                ghostnet_config.ghost_core,
-                ghostnet_config.hash_crash,
-                ghostnet_config.arcade_core,
-                ghostnet_config.data_token,
-                settings.chain.chain_id,
-            );
-
             let plugin = GhostnetPlugin::new(config, provider);
-            registry.register(Arc::new(plugin));
+            let priority = settings.plugins.priority("ghostnet");
+            registry.register_with_priority(Arc::new(plugin), priority)?;
             info!("Registered GHOSTNET plugin");
         }
 
-        registry
+        Ok(registry)
+    }
+
+    /// Create the behavior engine, acting under `leadership` if the fleet
+    /// runs behind a lease.
+    fn create_engine(
+        settings: &Settings,
+        registry: &PluginRegistry,
+        clock: &SharedClock,
+        seed: Option<u64>,
+        metrics: &Arc<FleetMetrics>,
+        leadership: Option<&Arc<Leadership>>,
+    ) -> BehaviorEngine {
+        let enabled = &settings.plugins.enabled;
+        let selection = settings.plugins.selection;
+        let engine = seed
+            .map_or_else(
+                || BehaviorEngine::new(registry, enabled, selection),
+                |seed| BehaviorEngine::with_seed(registry, enabled, selection, seed ^ 1),
+            )
+            .with_clock(Arc::clone(clock))
+            .with_retry_policy(RetryPolicy {
+                retries: settings.safety.transient_retries,
+                delay: Duration::from_millis(settings.safety.transient_retry_delay_ms),
+            })
+            .with_replacement_policy(replacement_policy(&settings.safety))
+            .with_action_timeouts(action_timeouts(&settings.safety))
+            .with_force_standard_submission(settings.chain.force_standard_submission)
+            .with_plugin_health(settings.plugins.health.to_settings())
+            .with_metrics(Arc::clone(metrics));
+        match leadership {
+            Some(leadership) => engine.with_leadership(Arc::clone(leadership)),
+            None => engine,
+        }
+    }
+
+    /// Create the per-wallet circuit breaker and the fleet-wide breaker of
+    /// rate-limited errors.
+    fn create_breakers(
+        settings: &Settings,
+        clock: &SharedClock,
+    ) -> (CircuitBreaker, GlobalBreaker) {
+        let safety = &settings.safety;
+        let circuit_breaker = CircuitBreaker::new(
+            safety.max_consecutive_errors,
+            Duration::from_secs(safety.cooldown_secs),
+        )
+        .with_clock(Arc::clone(clock));
+        let global_breaker = GlobalBreaker::new(
+            safety.max_rate_limited_errors,
+            Duration::from_secs(safety.rate_limit_window_secs),
+            Duration::from_secs(safety.rate_limit_backoff_secs),
+        )
+        .with_clock(Arc::clone(clock));
+        (circuit_breaker, global_breaker)
     }
 
-    /// Initialize wallet states from configuration.
-    fn initialize_wallets(settings: &Settings) -> HashMap<String, WalletState> {
+    /// Enabled wallets held in standby to replace retired ones, in config
+    /// order.
+    fn standby_wallets(settings: &Settings) -> Vec<WalletConfig> {
         settings
             .wallets
             .iter()
-            .filter(|w| w.enabled)
-            .map(|w| {
-                let state = WalletState::with_profile(
-                    w.id.clone(),
-                    w.address,
-                    w.profile.clone(),
-                );
-                (w.id.clone(), state)
-            })
+            .filter(|w| w.enabled && w.standby)
+            .cloned()
             .collect()
     }
 
-    /// Load behavior profiles from configuration.
-    fn load_profiles(settings: &Settings) -> HashMap<String, BehaviorProfile> {
+    /// Create the lease the fleet acts under, if `[leader]` is enabled.
+    fn create_leadership(settings: &Settings, clock: &SharedClock) -> Option<Arc<Leadership>> {
+        let leader = &settings.leader;
+        let path = leader.lease_file.as_ref().filter(|_| leader.enabled)?;
+        let leadership = Leadership::new(
+            Arc::new(FileLease::new(path.clone())),
+            leader.node_id(),
+            Duration::from_secs(leader.lease_ttl_secs),
+        )
+        .with_clock(Arc::clone(clock));
+        Some(Arc::new(leadership))
+    }
+
+    /// Create the group limiter from the configured group limits.
+    fn create_group_limiter(settings: &Settings) -> GroupLimiter {
+        settings
+            .groups
+            .iter()
+            .fold(GroupLimiter::new(), |limiter, (name, group)| {
+                limiter.with_limit(name.clone(), group.to_limit())
+            })
+    }
+
+    /// Initialize wallet states from configuration, all due at `now`.
+    ///
+    /// Standby wallets are held back until they replace a retired one.
+    fn initialize_wallets(
+        settings: &Settings,
+        now: DateTime<Utc>,
+        seed: Option<u64>,
+    ) -> HashMap<String, WalletState> {
+        let warmup = settings.warmup.to_settings();
         settings
-            .profiles
+            .wallets
             .iter()
-            .map(|(name, config)| (name.clone(), config.to_behavior_profile(name)))
+            .filter(|w| w.enabled && !w.standby)
+            .map(|w| (w.id.clone(), Self::wallet_from_config(w, &warmup, seed, now)))
             .collect()
     }
 
+    /// State of a configured wallet, due at `now`.
+    ///
+    /// New wallets start their warm-up if it is enabled, and wallets
+    /// configured as retiring start their retirement.
+    fn wallet_from_config(
+        config: &WalletConfig,
+        warmup: &WarmupSettings,
+        seed: Option<u64>,
+        now: DateTime<Utc>,
+    ) -> WalletState {
+        let mut state =
+            WalletState::with_profile(config.id.clone(), config.address, config.profile.clone());
+        state.group.clone_from(&config.group);
+        state.schedule_next(now);
+        let warmup_seed = personality_seed(config.address) ^ seed.unwrap_or(0);
+        if config.retiring {
+            state.start_retiring(config.sweep_to, now);
+            debug!(wallet = %config.id, "Retiring wallet");
+        } else if state.start_warmup(warmup, warmup_seed, now) {
+            debug!(wallet = %config.id, "Warming up new wallet");
+        }
+        state
+    }
+
+    /// Create the budget manager from the configured caps and the ledgers of
+    /// the wallet states.
+    fn create_budget(
+        settings: &Settings,
+        wallets: &HashMap<String, WalletState>,
+        clock: SharedClock,
+    ) -> BudgetManager {
+        let mut budget = BudgetManager::new(settings.safety.budget.to_caps()).with_clock(clock);
+        for wallet in &settings.wallets {
+            budget.set_caps(wallet.id.clone(), settings.wallet_budget(wallet));
+        }
+        for (id, wallet) in wallets {
+            budget.restore(id.clone(), wallet.budget.clone());
+        }
+        budget
+    }
+
+    /// Seed of a wallet's scheduling jitter.
+    ///
+    /// Derived from its address and last action, so a restart with the same
+    /// persisted state replays the same sequence; a seeded run mixes in the
+    /// run's seed.
+    #[allow(clippy::cast_sign_loss)] // Only the bits matter
+    fn jitter_seed(wallet: &WalletState, seed: Option<u64>) -> u64 {
+        let last_action = wallet
+            .last_action
+            .map_or(0, |at| at.timestamp_millis() as u64);
+        personality_seed(wallet.address) ^ last_action ^ seed.unwrap_or(0)
+    }
+
+    /// The wallet's profile: as composed at startup, or its personalization
+    /// of its behavior profile if it joined the fleet later.
+    fn profile_of(&self, wallet: &WalletState) -> Result<BehaviorProfile> {
+        if let Some(profile) = self.composition.get(&wallet.id)
+            && profile.name == wallet.profile_name
+        {
+            return Ok(profile.clone());
+        }
+        self.profiles
+            .personalized(&wallet.profile_name, personality_seed(wallet.address))
+            .context("Profile not found for wallet")
+    }
+
+    /// Compose the personalized profiles of the wallets to meet the
+    /// `[diversity]` targets, logging the distribution reached.
+    ///
+    /// Wallets whose profile does not resolve are left out.
+    fn compose_profiles(
+        settings: &Settings,
+        profiles: &ProfileCatalog,
+        wallets: &HashMap<String, WalletState>,
+    ) -> Composition {
+        let personalized = wallets.values().filter_map(|wallet| {
+            let profile =
+                profiles.personalized(&wallet.profile_name, personality_seed(wallet.address))?;
+            Some((wallet.id.clone(), profile))
+        });
+        let diversity = &settings.diversity;
+        let composition =
+            FleetComposer::new(diversity.to_targets(), diversity.seed).compose(personalized);
+
+        let report = composition.report();
+        if report.is_satisfied() {
+            info!(
+                wallets = report.wallets,
+                adjusted = report.adjusted_wallets,
+                "Fleet profiles composed"
+            );
+        } else {
+            warn!(
+                wallets = report.wallets,
+                adjusted = report.adjusted_wallets,
+                coverage_met = report.coverage_met(),
+                hourly_coverage = ?report.hourly_coverage,
+                risk_met = report.risk_met(),
+                risk_histogram = ?report.risk_histogram,
+                intervals_met = report.intervals_met(),
+                max_shared_intervals = report.max_shared_intervals,
+                "Fleet profiles miss diversity targets"
+            );
+        }
+        composition
+    }
+
+    /// Load behavior profiles from configuration.
+    ///
+    /// Settings are validated before the service is created, so profiles
+    /// only fail to resolve if validation was skipped; their wallets then
+    /// fail to find a profile.
+    fn load_profiles(settings: &Settings) -> ProfileCatalog {
+        settings.profile_catalog().unwrap_or_else(|e| {
+            warn!(error = %e, "Behavior profiles do not resolve");
+            ProfileCatalog::new()
+        })
+    }
+
+    /// State of the wallets and the circuit breaker, to be saved, see
+    /// [`state`](crate::state).
+    #[must_use]
+    pub fn state(&self) -> FleetState {
+        FleetState::new(
+            self.clock.now(),
+            self.wallets.clone(),
+            self.circuit_breaker.snapshot(),
+        )
+    }
+
+    /// Pick up the state saved by an earlier run, before the first tick.
+    ///
+    /// Saved wallets that are no longer configured, or now have another
+    /// address, are left out; configured wallets that were not saved start
+    /// afresh. The wallets keep their groups and, unless they were activated
+    /// from standby, their profiles from the config. Trips of the circuit
+    /// breaker keep their original trip time, so cooldowns continue rather
+    /// than restart.
+    pub fn restore_state(&mut self, state: FleetState) {
+        let now = self.clock.now();
+        let mut restored = 0;
+        for (id, mut saved) in state.wallets {
+            let Some(config) = self.settings.wallets.iter().find(|w| w.id == id && w.enabled)
+            else {
+                continue;
+            };
+            if config.address != saved.address {
+                warn!(wallet = %id, "Saved wallet has another address, starting afresh");
+                continue;
+            }
+            saved.group.clone_from(&config.group);
+            if config.standby {
+                // Activated from standby by an earlier run
+                let Some(at) = self.standby.iter().position(|w| w.id == id) else {
+                    continue;
+                };
+                let mut config = self.standby.remove(at);
+                config.profile.clone_from(&saved.profile_name);
+                config.standby = false;
+                if let Some(configured) = self.settings.wallets.iter_mut().find(|w| w.id == id)
+                {
+                    configured.clone_from(&config);
+                }
+            } else {
+                saved.profile_name.clone_from(&config.profile);
+                if config.retiring {
+                    saved.start_retiring(config.sweep_to, now);
+                }
+            }
+            self.scheduler
+                .seed_wallet(id.clone(), Self::jitter_seed(&saved, self.seed));
+            self.wallets.insert(id, saved);
+            restored += 1;
+        }
+
+        self.budget = Self::create_budget(&self.settings, &self.wallets, Arc::clone(&self.clock));
+        let wallets = &self.wallets;
+        self.circuit_breaker
+            .restore(state.circuit_breaker.retain(|id| wallets.contains_key(id)));
+        self.reports.rebase(self.report_snapshot());
+
+        info!(
+            version = state.version,
+            saved_at = ?state.saved_at,
+            wallets = restored,
+            tripped = self.circuit_breaker.tripped_count(),
+            "Fleet state restored"
+        );
+    }
+
+    /// Restore the PnL ledger kept in `service.pnl_file` and the state
+    /// saved to `service.state_file`, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the ledger or state file exists but cannot be
+    /// read.
+    pub fn load_state(&mut self) -> Result<()> {
+        if let Some(path) = self.settings.service.pnl_file_of(self.fleet_id.as_deref()) {
+            self.pnl = PnlLedger::open(&path)?;
+            self.reports.rebase(self.report_snapshot());
+        }
+        let Some(path) = self.state_path() else {
+            return Ok(());
+        };
+        if let Some(state) = state::load(&path)? {
+            self.restore_state(state);
+        } else {
+            info!(path = %path.display(), "No saved fleet state, starting afresh");
+        }
+        Ok(())
+    }
+
+    /// Save the state to `service.state_file`, if set. A state that cannot
+    /// be saved is logged, but does not hold up the fleet.
+    pub fn save_state(&self) {
+        let Some(path) = self.state_path() else {
+            return;
+        };
+        match state::save(&path, &self.state()) {
+            Ok(()) => debug!(path = %path.display(), "Fleet state saved"),
+            Err(e) => warn!(error = %e, "Failed to save fleet state"),
+        }
+    }
+
+    /// The state file of this fleet, if one is configured.
+    fn state_path(&self) -> Option<PathBuf> {
+        self.settings
+            .service
+            .state_file_of(self.fleet_id.as_deref())
+    }
+
+    /// Whether this node acts for the fleet: always, unless `[leader]` is
+    /// enabled and it does not hold the fleet's lease.
+    #[must_use]
+    pub fn is_leader(&self) -> bool {
+        self.leadership
+            .as_ref()
+            .is_none_or(|leadership| leadership.is_leader())
+    }
+
+    /// Renew the fleet's lease, or take it if it is free, and take over the
+    /// fleet or stand by as the lease changed hands since the last
+    /// heartbeat.
+    pub async fn heartbeat(&mut self) {
+        let Some(leadership) = self.leadership.clone() else {
+            return;
+        };
+        if let Err(e) = leadership.heartbeat().await {
+            warn!(error = %e, "Fleet lease heartbeat failed");
+        }
+        match (self.leading, leadership.term()) {
+            (Some(led), Some(term)) if led == term => {}
+            (led, Some(term)) => {
+                // Another node may have led since this node's last term
+                if let Some(led) = led {
+                    self.stand_by(led);
+                }
+                self.take_over(term);
+            }
+            (Some(led), None) => self.stand_by(led),
+            (None, None) => {}
+        }
+    }
+
+    /// Start leading the fleet in `term`, from the state the last leader
+    /// saved.
+    ///
+    /// Nonces held back or state taken from receipts are dropped, so every
+    /// wallet reads its nonce and state from the chain before it acts, and
+    /// the wallets found overdue ramp up as after a cold start.
+    fn take_over(&mut self, term: u64) {
+        let node = self.leadership.as_ref().map_or("", |l| l.node()).to_string();
+        info!(node = %node, term, "Took over the fleet lease");
+        self.review.record_lease_acquired(&node, term, self.clock.now());
+        if let Err(e) = self.load_state() {
+            warn!(error = %e, "Failed to load the fleet state, keeping the last one followed");
+        }
+        self.nonce_floors.clear();
+        self.receipt_states.clear();
+        self.started = false;
+        self.cold_start = None;
+        self.leading = Some(term);
+    }
+
+    /// Stop leading the fleet after losing the lease of `term`.
+    fn stand_by(&mut self, term: u64) {
+        let node = self.leadership.as_ref().map_or("", |l| l.node()).to_string();
+        warn!(node = %node, term, "Lost the fleet lease, standing by");
+        self.review.record_lease_lost(&node, term, self.clock.now());
+        self.leading = None;
+        self.started = false;
+        self.cold_start = None;
+    }
+
+    /// Follow the state the leader saved while standing by, re-reading the
+    /// wallets from the chain with `leader.standby_refresh` set.
+    async fn follow_leader(&mut self) {
+        if let Err(e) = self.load_state() {
+            warn!(error = %e, "Failed to follow the fleet state");
+        }
+        if !self.settings.leader.standby_refresh {
+            return;
+        }
+        let mut wallet_ids: Vec<_> = self.wallets.keys().cloned().collect();
+        wallet_ids.sort_unstable();
+        for wallet_id in wallet_ids {
+            if let Err(e) = self.refresh_wallet_state(&wallet_id).await {
+                debug!(wallet = %wallet_id, error = %e, "Failed to refresh standby wallet");
+            }
+        }
+    }
+
     /// Run the service main loop.
     ///
     /// This method runs until the shutdown signal is received or an
-    /// unrecoverable error occurs.
+    /// unrecoverable error occurs. The state is saved every
+    /// `service.state_save_interval_secs` and on shutdown; with `[leader]`
+    /// enabled, only while leading, and a standby loads it instead.
     ///
     /// # Arguments
     ///
@@ -322,20 +1170,56 @@ impl FleetService {
     pub async fn run(mut self, mut shutdown: watch::Receiver<bool>) -> Result<()> {
         let tick_duration = Duration::from_millis(self.settings.service.tick_interval_ms);
         let mut tick = interval(tick_duration);
+        let mut save = interval(Duration::from_secs(
+            self.settings.service.state_save_interval_secs.max(1),
+        ));
+        let mut heartbeat = interval(Duration::from_secs(
+            self.settings.leader.heartbeat_interval_secs.max(1),
+        ));
+
+        let mut control = self.control.take();
 
         info!(
             tick_ms = self.settings.service.tick_interval_ms,
+            control = control.is_some(),
             "Starting main loop"
         );
 
         loop {
             tokio::select! {
+                // Commands first, so none waits behind a run of ticks
+                biased;
+                Some((command, reply)) = next_command(&mut control) => {
+                    let response = self.handle_command(command).await;
+                    // The client may have gone away in the meantime
+                    let _ = reply.send(response);
+                }
+                // Ahead of the ticks, so the first one knows who leads
+                _ = heartbeat.tick(), if self.leadership.is_some() => {
+                    self.heartbeat().await;
+                }
                 _ = tick.tick() => {
                     self.process_tick().await;
                 }
+                _ = save.tick() => {
+                    if self.is_leader() {
+                        self.save_state();
+                    } else {
+                        self.follow_leader().await;
+                    }
+                }
                 _ = shutdown.changed() => {
                     if *shutdown.borrow() {
                         info!("Shutdown signal received, stopping service");
+                        if self.is_leader() {
+                            self.save_state();
+                        }
+                        // Hand over at once rather than after the lease's TTL
+                        if let Some(leadership) = &self.leadership
+                            && let Err(e) = leadership.release().await
+                        {
+                            warn!(error = %e, "Failed to release the fleet lease");
+                        }
                         return Ok(());
                     }
                 }
@@ -343,13 +1227,55 @@ impl FleetService {
         }
     }
 
-    /// Process a single tick of the main loop.
-    async fn process_tick(&mut self) {
+    /// Process a single tick of the main loop. A node standing by for the
+    /// fleet's lease does nothing.
+    pub async fn process_tick(&mut self) {
+        // Events the fleet cannot act on right away are dropped, not woken on
+        // late
+        let events = self.take_protocol_events();
+        if !self.is_leader() {
+            return;
+        }
+
+        // Days close even while the fleet is paused
+        if self.reports.is_due_at(self.clock.now()) {
+            let snapshot = self.report_snapshot();
+            self.reports.close_day(snapshot);
+        }
+
+        // Probe the endpoints, and only probe while they cannot even be read
+        self.probe_endpoints().await;
+        if self.degradation_tier() == DegradationTier::Paused {
+            debug!("RPC unavailable, skipping tick");
+            return;
+        }
+
+        // Spread the wallets that came back overdue before any of them runs
+        if !self.started {
+            self.started = true;
+            self.plan_cold_start();
+        }
+        if let Some(ramp) = &self.cold_start
+            && ramp.is_over_at(self.clock.now())
+        {
+            let progress = ramp.progress();
+            info!(
+                started = progress.started,
+                wallets = progress.wallets,
+                "Cold-start ramp complete"
+            );
+            self.cold_start = None;
+        }
+
         // Check global pause
         if self.settings.safety.global_pause {
             debug!("Global pause active, skipping tick");
             return;
         }
+        if let Some(until) = self.global_breaker.open_until() {
+            debug!(until = %until, "Global breaker open, skipping tick");
+            return;
+        }
 
         // Auto-reset circuit breakers
         let reset_count = self.circuit_breaker.check_auto_reset();
@@ -357,6 +1283,17 @@ impl FleetService {
             info!(count = reset_count, "Auto-reset circuit breakers");
         }
 
+        // Wake the wallets of the protocol events since the last tick
+        for event in &events {
+            self.handle_protocol_event(event);
+        }
+
+        // Run the reviewed batches before planning the next one, once they
+        // may run at all
+        if self.review.is_gated() && self.degradation_tier() == DegradationTier::Normal {
+            self.execute_reviewed().await;
+        }
+
         // Get wallets due for action
         let due_wallets = self.get_due_wallets();
 
@@ -365,21 +1302,258 @@ impl FleetService {
         }
 
         // Process each due wallet
+        let acted = !due_wallets.is_empty();
         for wallet_id in due_wallets {
+            // The lease may have been lost while acting
+            if !self.is_leader() {
+                break;
+            }
             if let Err(e) = self.process_wallet(&wallet_id).await {
                 error!(wallet = %wallet_id, error = %e, "Error processing wallet");
             }
         }
+        if acted {
+            self.check_correlation();
+        }
+
+        self.review.seal(self.clock.now());
     }
 
-    /// Get IDs of wallets that are due for action.
-    fn get_due_wallets(&self) -> Vec<String> {
-        self.wallets
+    /// Probe the RPC endpoints if due, and move the fleet to the tier they
+    /// put it in, see [`crate::degradation`].
+    ///
+    /// While degraded, the circuit breakers are frozen and the global
+    /// breaker is closed, so the outage counts against neither.
+    async fn probe_endpoints(&mut self) {
+        let now = self.clock.now();
+        let Some(monitor) = &mut self.degradation else {
+            return;
+        };
+        let change = monitor.probe_if_due(&self.pool, now).await;
+        self.engine.set_realtime_down(monitor.realtime_down());
+        let Some(change) = change else {
+            return;
+        };
+        monitor.alert(self.fleet_id.as_deref(), &change);
+
+        let (from, to) = (change.from, change.to);
+        let (reads, sends) = (change.capabilities.reads, change.capabilities.sends);
+        match to {
+            DegradationTier::Normal => warn!(%from, %to, "RPC recovered, fleet resumes"),
+            DegradationTier::ReadOnly if change.is_recovery() => {
+                warn!(%from, %to, reads, sends, "RPC reads recovered, fleet is read-only");
+            }
+            DegradationTier::ReadOnly => {
+                error!(%from, %to, reads, sends, "RPC sends failing, fleet is read-only");
+            }
+            DegradationTier::Paused => {
+                error!(%from, %to, reads, sends, "RPC unavailable, fleet paused");
+            }
+        }
+        self.review.record_tier_changed(from, to, change.at);
+
+        if to == DegradationTier::Normal {
+            self.circuit_breaker.thaw();
+        } else {
+            self.circuit_breaker.freeze();
+            self.global_breaker.reset();
+        }
+        // Wallets that fell due while paused ramp up as after a restart
+        if from == DegradationTier::Paused {
+            self.started = false;
+            self.cold_start = None;
+        }
+    }
+
+    /// Degradation tier the fleet is in, normal without `[degradation]`.
+    fn degradation_tier(&self) -> DegradationTier {
+        self.degradation
+            .as_ref()
+            .map_or(DegradationTier::Normal, DegradationMonitor::tier)
+    }
+
+    /// Journal and log a wallet's `action` as withheld if the fleet is
+    /// degraded, returning whether it is.
+    fn withhold(&self, plugin_id: &str, wallet_id: &str, action: &Action) -> bool {
+        let tier = self.degradation_tier();
+        if tier == DegradationTier::Normal {
+            return false;
+        }
+        info!(action = %action.name, plugin = plugin_id, %tier, "RPC degraded, withholding action");
+        self.review
+            .record_withheld(wallet_id, plugin_id, &action.id, tier, self.clock.now());
+        true
+    }
+
+    /// Take the protocol events received since the last tick.
+    fn take_protocol_events(&mut self) -> Vec<ProtocolEvent> {
+        let mut events = Vec::new();
+        if let Some(receiver) = &mut self.events {
+            while let Ok(event) = receiver.try_recv() {
+                events.push(event);
+            }
+        }
+        events
+    }
+
+    /// Move the next action of the wallets `event` wakes earlier, see
+    /// [`crate::trigger`].
+    ///
+    /// Wallets that are AFK, tripped or have an action under review are not
+    /// woken, and a wakeup never puts a wallet's next action off.
+    fn handle_protocol_event(&mut self, event: &ProtocolEvent) {
+        let Some(trigger) = &mut self.trigger else {
+            return;
+        };
+        let now = self.clock.now();
+        let mut candidates: Vec<_> = self
+            .wallets
+            .values()
+            .filter(|w| w.is_active_at(now))
+            .filter(|w| !self.circuit_breaker.is_tripped(&w.id))
+            .filter(|w| !self.review.is_pending(&w.id))
+            .collect();
+        // Stable order keeps seeded runs reproducible
+        candidates.sort_unstable_by(|a, b| a.id.cmp(&b.id));
+
+        let wakeups = trigger.wakeups(event, candidates, self.engine.plugins());
+        let mut woken = 0;
+        for wakeup in wakeups {
+            if let Some(w) = self.wallets.get_mut(&wakeup.wallet_id)
+                && wakeup.at < w.next_action
+            {
+                w.schedule_next(wakeup.at);
+                self.woken.insert(wakeup.wallet_id);
+                woken += 1;
+            }
+        }
+        info!(event = event.name(), woken, "Woke wallets");
+    }
+
+    /// Warn when the wallets' recent actions crowd into the same time
+    /// buckets, and note when they spread out again.
+    fn check_correlation(&mut self) {
+        let correlation = self.metrics.correlation();
+        if correlation.flagged == self.correlated {
+            return;
+        }
+        self.correlated = correlation.flagged;
+        if correlation.flagged {
+            warn!(
+                actions = correlation.actions,
+                busiest_bucket = correlation.busiest_bucket_actions,
+                concentration = correlation.concentration,
+                "Wallets are acting in lockstep"
+            );
+        } else {
+            info!(
+                concentration = correlation.concentration,
+                "Wallet activity spread out again"
+            );
+        }
+    }
+
+    /// Reschedule the wallets that are overdue at startup over the
+    /// `[cold_start]` ramp window.
+    ///
+    /// Wallets still AFK or tripped are left to their own time.
+    fn plan_cold_start(&mut self) {
+        if !self.settings.cold_start.enabled {
+            return;
+        }
+        let now = self.clock.now();
+        let mut overdue: Vec<_> = self
+            .wallets
+            .values()
+            .filter(|w| w.is_active_at(now) && w.next_action < now)
+            .filter(|w| !self.circuit_breaker.is_tripped(&w.id))
+            .filter_map(|w| {
+                let profile = self.profile_of(w).ok()?;
+                Some(OverdueWallet {
+                    id: w.id.clone(),
+                    overdue: now - w.next_action,
+                    activity_level: profile.activity_level,
+                })
+            })
+            .collect();
+        if overdue.is_empty() {
+            return;
+        }
+        // Stable order keeps seeded runs reproducible
+        overdue.sort_unstable_by(|a, b| a.id.cmp(&b.id));
+
+        let settings = self.settings.cold_start.to_settings();
+        let (ramp, schedule) = self.scheduler.plan_ramp(&settings, overdue);
+        for (wallet_id, at) in schedule {
+            if let Some(w) = self.wallets.get_mut(&wallet_id) {
+                w.schedule_next(at);
+            }
+        }
+        let progress = ramp.progress();
+        info!(
+            wallets = progress.wallets,
+            window_secs = self.settings.cold_start.ramp_window_secs,
+            ends_at = %progress.ends_at,
+            "Ramping up overdue wallets"
+        );
+        self.cold_start = Some(ramp);
+    }
+
+    /// Get IDs of wallets that are due for action, in ID order.
+    ///
+    /// Wallets with an action under review are not due.
+    fn get_due_wallets(&self) -> Vec<String> {
+        let now = self.clock.now();
+        let mut due: Vec<_> = self
+            .wallets
             .values()
-            .filter(|w| w.is_active() && w.is_due())
+            .filter(|w| w.is_active_at(now) && w.is_due_at(now))
             .filter(|w| !self.circuit_breaker.is_tripped(&w.id))
+            .filter(|w| !self.review.is_pending(&w.id))
             .map(|w| w.id.clone())
-            .collect()
+            .collect();
+        // Stable order keeps seeded runs reproducible
+        due.sort_unstable();
+        due
+    }
+
+    /// Earliest time at which a wallet becomes due or a tripped circuit
+    /// breaker resets, but not before the global breaker closes. With
+    /// `[degradation]` enabled, the next probe of the endpoints is due by
+    /// then, and while the fleet is paused, nothing else.
+    ///
+    /// Returns `None` if no enabled wallet will ever act again.
+    #[must_use]
+    pub fn next_wakeup(&self) -> Option<DateTime<Utc>> {
+        let now = self.clock.now();
+        let next = self
+            .wallets
+            .values()
+            .filter(|w| w.active)
+            .map(|w| {
+                self.circuit_breaker
+                    .time_until_reset(&w.id)
+                    .map_or(w.next_action, |left| {
+                        // Auto-reset requires the cooldown to have fully elapsed
+                        chrono::Duration::from_std(left)
+                            .ok()
+                            .and_then(|left| {
+                                now.checked_add_signed(left + chrono::Duration::milliseconds(1))
+                            })
+                            .unwrap_or(DateTime::<Utc>::MAX_UTC)
+                    })
+            })
+            .min()
+            .map(|next| {
+                self.global_breaker
+                    .open_until()
+                    .map_or(next, |until| next.max(until))
+            })?;
+        Some(match &self.degradation {
+            None => next,
+            Some(monitor) if monitor.tier() == DegradationTier::Paused => monitor.next_probe(),
+            Some(monitor) => next.min(monitor.next_probe()),
+        })
     }
 
     /// Process a single wallet.
@@ -397,100 +1571,115 @@ impl FleetService {
             return Ok(());
         }
 
-        // Get profile name first (clone to avoid borrow issues)
-        let profile_name = {
+        // Check the wallet's group limit
+        if self.defer_for_group_limit(wallet_id) {
+            return Ok(());
+        }
+
+        // Get the wallet's profile (cloned to avoid borrow issues)
+        let profile = {
             let wallet = self.wallets.get(wallet_id)
                 .context("Wallet not found")?;
-            wallet.profile_name.clone()
+            self.profile_of(wallet)?
         };
 
-        // Get profile (clone to avoid borrow issues)
-        let profile = self.profiles.get(&profile_name)
-            .cloned()
-            .context("Profile not found for wallet")?;
-
-        // Check if we should act based on active hours
-        if !self.scheduler.should_act_now(&profile) {
+        // Check if we should act based on active hours; a wallet woken by a
+        // protocol event may still run urgent actions outside them
+        let urgent_wake = self.woken.remove(wallet_id)
+            && self
+                .trigger
+                .as_ref()
+                .is_some_and(EventTrigger::has_urgent_actions);
+        let urgent_only = !self.scheduler.should_act_now(&profile);
+        if urgent_only && !urgent_wake {
             debug!("Outside active hours, scheduling next action");
-            let next = self.scheduler.calculate_next_action(&profile);
-            if let Some(w) = self.wallets.get_mut(wallet_id) {
-                w.schedule_next(next);
-            }
+            self.schedule_next_action(wallet_id, &profile);
+            return Ok(());
+        }
+
+        // Meter the first reads of wallets ramping up after a restart
+        if self.defer_for_ramp_reads(wallet_id) {
             return Ok(());
         }
 
         // Refresh wallet state from chain
         self.refresh_wallet_state(wallet_id).await?;
+        self.advance_cold_start(wallet_id);
 
-        // Check for AFK
-        if let Some(afk_until) = self.scheduler.maybe_go_afk(&profile) {
-            info!(until = %afk_until, "Wallet going AFK");
-            if let Some(w) = self.wallets.get_mut(wallet_id) {
-                w.set_afk(afk_until);
-                w.schedule_next(afk_until);
-            }
+        if self.maybe_go_afk(wallet_id, &profile) {
             return Ok(());
         }
 
+        // Stretch a stalled warm-up, and return warmed up wallets to normal
+        let now = self.clock.now();
+        if let Some(w) = self.wallets.get_mut(wallet_id)
+            && w.advance_warmup(now)
+        {
+            info!("Wallet warm-up complete");
+        }
+
         // Get wallet for action decision (clone to avoid borrow issues)
         let wallet = self.wallets.get(wallet_id)
             .cloned()
             .context("Wallet not found")?;
 
-        // Decide action via behavior engine
-        let action_decision = self.engine.decide_action(&wallet, &profile).await;
+        // A retiring wallet with nothing left open winds down instead
+        if !urgent_only && wallet.is_retiring() && !self.engine.has_open_positions(&wallet) {
+            return self.wind_down(&wallet, &profile).await;
+        }
+
+        // A due follow-up runs in place of a decision, within active hours
+        let due = if urgent_only {
+            None
+        } else {
+            self.due_follow_up(wallet_id, &profile)
+        };
+        let (mut action_decision, follow_up) = match due {
+            Some((plugin, pending)) => (Some((plugin, pending.action())), Some(pending)),
+            None => (self.engine.decide_action(&wallet, &profile).await, None),
+        };
+        if urgent_only
+            && let Some((plugin, action)) = &action_decision
+            && !self.is_urgent(plugin.as_ref(), &action.id)
+        {
+            debug!(action = %action.name, "Outside active hours and not urgent, skipping");
+            action_decision = None;
+        }
+        let mut not_before = None;
 
         match action_decision {
+            Some((plugin, action)) if action.is_refresh_request() => {
+                // Not an on-chain action: re-read the balances and decide again
+                // next time round
+                let tokens = action.refresh_tokens().unwrap_or_default();
+                debug!(plugin = plugin.id(), tokens = ?tokens, "Balance refresh requested");
+                self.refresh_token_balances(wallet_id, tokens, chrono::Duration::zero())
+                    .await;
+            }
             Some((plugin, action)) => {
                 info!(
                     action = %action.name,
                     plugin = plugin.id(),
+                    chain_depth = action.chain_depth,
                     "Action decided"
                 );
-
-                if self.dry_run {
-                    info!(action = %action.name, "DRY RUN: Would execute action");
-                    // Still record for rate limiting in dry run
-                    self.rate_limiter.record_action(wallet_id);
-                } else {
-                    // Execute the action
-                    let result = plugin.execute_action(&action, &wallet, wallet.nonce).await;
-
-                    match result {
-                        Ok(action_result) => {
-                            if action_result.success {
-                                info!(
-                                    tx_hash = ?action_result.tx_hash,
-                                    "Action executed successfully"
-                                );
-                                self.circuit_breaker.record_success(wallet_id);
-                                self.rate_limiter.record_action(wallet_id);
-                                if let Some(w) = self.wallets.get_mut(wallet_id) {
-                                    w.record_success();
-                                    w.increment_nonce();
-                                }
-                            } else {
-                                warn!(
-                                    error = ?action_result.error,
-                                    "Action failed"
-                                );
-                                self.record_wallet_error(wallet_id);
-                            }
-                        }
-                        Err(e) => {
-                            error!(error = %e, "Action execution error");
-                            self.record_wallet_error(wallet_id);
-                        }
-                    }
-                }
+                not_before = self.run_decided(plugin.as_ref(), &action, &wallet, follow_up).await;
             }
             None => {
                 debug!("No action decided");
             }
         }
+        self.record_settled_flows(&wallet);
 
-        // Schedule next action
-        let next = self.scheduler.calculate_next_action(&profile);
+        // Schedule next action, or the first follow-up if sooner, backing off
+        // if the endpoint asked us to
+        let mut next = self.scheduler.calculate_next_action(wallet_id, &profile);
+        if let Some(due) = self.wallets.get(wallet_id).and_then(WalletState::next_follow_up_due) {
+            next = next.min(due);
+        }
+        if let Some(earliest) = not_before {
+            next = next.max(self.scheduler.delay_until(earliest));
+        }
         if let Some(w) = self.wallets.get_mut(wallet_id) {
             w.schedule_next(next);
         }
@@ -498,6 +1687,629 @@ impl FleetService {
         Ok(())
     }
 
+    /// Plan, simulate or execute a decided action, as the service runs, or
+    /// withhold it while the fleet is degraded.
+    ///
+    /// A follow-up the budget holds back is queued again for when the budget
+    /// allows spending, and a withheld one for the next probe. Returns the
+    /// earliest time the wallet may act again, if it has to back off.
+    async fn run_decided(
+        &mut self,
+        plugin: &dyn ActionPlugin,
+        action: &Action,
+        wallet: &WalletState,
+        follow_up: Option<PendingFollowUp>,
+    ) -> Option<DateTime<Utc>> {
+        if self.withhold(plugin.id(), &wallet.id, action) {
+            // Tried again once the fleet may be back to normal
+            let retry_at = self
+                .degradation
+                .as_ref()
+                .map_or_else(|| self.clock.now(), DegradationMonitor::next_probe);
+            if let Some(mut pending) = follow_up
+                && let Some(w) = self.wallets.get_mut(&wallet.id)
+            {
+                pending.due = retry_at;
+                w.queue_follow_up(pending);
+            }
+            return None;
+        }
+        if self.review.is_gated() {
+            let estimate = plugin.estimate_spend(action);
+            self.review
+                .plan(PlannedAction::new(&wallet.id, plugin.id(), action, estimate));
+            return None;
+        }
+        if self.dry_run {
+            self.simulate_action(plugin.id(), action, wallet);
+            return None;
+        }
+
+        let execution = self.execute_action(plugin, action, wallet).await;
+        if let Some(mut pending) = follow_up
+            && let Some(w) = self.wallets.get_mut(&wallet.id)
+        {
+            match &execution {
+                Execution::OverBudget { resumes_at: Some(at) } => {
+                    pending.due = *at;
+                    w.queue_follow_up(pending);
+                }
+                // Left for the next leader, as it was
+                Execution::Aborted => w.queue_follow_up(pending),
+                _ => {}
+            }
+        }
+        execution.not_before()
+    }
+
+    /// Take a wallet's first due follow-up that may run now, with the plugin
+    /// that runs it.
+    ///
+    /// Follow-ups of plugins no longer enabled, and follow-ups other than
+    /// exits of retiring wallets, are dropped; one still on cooldown, or of a
+    /// disabled plugin, is queued again for when it may run.
+    fn due_follow_up(
+        &mut self,
+        wallet_id: &str,
+        profile: &BehaviorProfile,
+    ) -> Option<(Arc<dyn ActionPlugin>, PendingFollowUp)> {
+        let now = self.clock.now();
+        let wallet = self.wallets.get_mut(wallet_id)?;
+        while let Some(mut pending) = wallet.take_due_follow_up(now) {
+            let action = &pending.follow_up.action;
+            let Some(plugin) = self
+                .engine
+                .plugins()
+                .iter()
+                .find(|plugin| plugin.id() == pending.plugin_id)
+            else {
+                warn!(
+                    action = %action,
+                    plugin = %pending.plugin_id,
+                    "Plugin of follow-up not enabled, dropping it"
+                );
+                continue;
+            };
+            if wallet.is_retiring() && !plugin.is_exit_action(action) {
+                debug!(action = %action, "Retiring wallet only exits, dropping follow-up");
+                continue;
+            }
+            if let Some(until) = self.engine.plugin_disabled_until(plugin.id()) {
+                debug!(
+                    action = %action,
+                    until = %until,
+                    "Plugin disabled, queueing follow-up again"
+                );
+                pending.due = until;
+                wallet.queue_follow_up(pending);
+                continue;
+            }
+            let cooldowns = ActionCooldowns::resolve(self.engine.plugins(), profile, wallet);
+            if let Some(left) = cooldowns.remaining(action.as_str(), now) {
+                debug!(action = %action, left = %left, "Follow-up on cooldown, queueing it again");
+                pending.due = now + left;
+                wallet.queue_follow_up(pending);
+                continue;
+            }
+            return Some((Arc::clone(plugin), pending));
+        }
+        None
+    }
+
+    /// Queue the follow-ups of an action whose outcome was `status`.
+    fn queue_follow_ups(
+        &mut self,
+        plugin_id: &str,
+        wallet_id: &str,
+        action: &Action,
+        status: ActionStatus,
+    ) {
+        if action.follow_up.is_empty() {
+            return;
+        }
+        if action.chain_depth >= MAX_CHAIN_DEPTH {
+            warn!(
+                action = %action.id,
+                depth = action.chain_depth,
+                "Follow-up chain too deep, dropping its follow-ups"
+            );
+            return;
+        }
+        let scheduler = &mut self.scheduler;
+        let queued =
+            PendingFollowUp::after(plugin_id, action, status, |f| scheduler.follow_up_at(f));
+        let Some(w) = self.wallets.get_mut(wallet_id) else {
+            return;
+        };
+        for pending in queued {
+            info!(
+                action = %pending.follow_up.action,
+                due = %pending.due,
+                depth = pending.depth,
+                "Follow-up queued"
+            );
+            w.queue_follow_up(pending);
+        }
+    }
+
+    /// Sweep one of a retiring wallet's balances above dust, or retire the
+    /// wallet if it holds only dust.
+    ///
+    /// Tokens are swept before the native balance, which pays their gas.
+    /// Without a sweep address, a wallet holding more than dust stays
+    /// retiring until its balances were moved out some other way.
+    async fn wind_down(&mut self, wallet: &WalletState, profile: &BehaviorProfile) -> Result<()> {
+        let dust = self.settings.retirement.to_settings();
+        let tokens: Vec<_> = self.settings.chain.ghostnet.data_token.into_iter().collect();
+        if wallet.holds_only_dust(&dust, &tokens) {
+            self.retire_wallet(&wallet.id);
+            return Ok(());
+        }
+
+        let not_before = if let Some(to) = wallet.retirement.and_then(|r| r.sweep_to) {
+            let asset = tokens
+                .iter()
+                .copied()
+                .find(|token| !dust.is_dust_token(wallet.token_balance(*token)))
+                .map_or(SweepAsset::Native, SweepAsset::Token);
+            self.sweep(wallet, asset, to).await
+        } else {
+            debug!("Retiring wallet holds more than dust and has no sweep address");
+            None
+        };
+
+        let mut next = self.scheduler.calculate_next_action(&wallet.id, profile);
+        if let Some(earliest) = not_before {
+            next = next.max(self.scheduler.delay_until(earliest));
+        }
+        if let Some(w) = self.wallets.get_mut(&wallet.id) {
+            w.schedule_next(next);
+        }
+        Ok(())
+    }
+
+    /// Sweep what a retiring wallet holds of `asset` to `to`, recording the
+    /// outcome like that of an action.
+    ///
+    /// Returns the earliest time the wallet may act again, if it has to back
+    /// off.
+    async fn sweep(
+        &mut self,
+        wallet: &WalletState,
+        asset: SweepAsset,
+        to: Address,
+    ) -> Option<DateTime<Utc>> {
+        let action = Action::new(ACTION_SWEEP, "Sweep");
+        if self.dry_run {
+            info!(asset = ?asset, to = %to, "DRY RUN: Would sweep");
+            let simulated = ActionResult::simulated();
+            self.metrics
+                .record_result(ENGINE_ID, ACTION_SWEEP, &wallet.id, &simulated);
+            return None;
+        }
+        if self.withhold(ENGINE_ID, &wallet.id, &action) {
+            return None;
+        }
+        let Some(signer) = self.signers.get(&wallet.id) else {
+            let error = FleetServiceError::NoSigner(wallet.id.clone());
+            error!(error = %error, "Cannot sweep");
+            let failure = ActionResult::failure(error.to_string());
+            self.record_action_result(ENGINE_ID, &wallet.id, &action, &failure);
+            return None;
+        };
+        let estimate = Spend::action();
+        if !self.budget.can_spend(&wallet.id, &estimate) {
+            info!(status = ?self.budget.status(&wallet.id), "Budget exhausted, skipping sweep");
+            return self.budget.resumes_at(&wallet.id);
+        }
+
+        let chain = self.pool.provider_for(wallet.address);
+        let started = Instant::now();
+        let swept = self
+            .engine
+            .sweep(wallet, &*chain, signer.signer(), asset, to)
+            .await;
+        match swept {
+            Ok(result) => {
+                let result = result.with_duration(started.elapsed());
+                info!(asset = ?asset, to = %to, status = %result.status, "Swept retiring wallet");
+                self.record_spend(&wallet.id, &Spend::actual(&result, &estimate));
+                self.record_pnl(&wallet.id, &action, &result);
+                self.record_action_result(ENGINE_ID, &wallet.id, &action, &result);
+                // The next turn must not sweep the tokens again
+                if let SweepAsset::Token(token) = asset
+                    && result.status == ActionStatus::Succeeded
+                {
+                    self.refresh_token_balances(&wallet.id, vec![token], chrono::Duration::zero())
+                        .await;
+                }
+                None
+            }
+            Err(FleetError::LeaseLost) => None,
+            Err(e) => {
+                let elapsed = started.elapsed();
+                self.handle_action_error(ENGINE_ID, &wallet.id, &action, &e, elapsed).1
+            }
+        }
+    }
+
+    /// Retire a retiring wallet, journal it, and put a standby wallet in its
+    /// place.
+    fn retire_wallet(&mut self, wallet_id: &str) {
+        let now = self.clock.now();
+        let Some(wallet) = self.wallets.get_mut(wallet_id) else {
+            return;
+        };
+        if !wallet.retire(now) {
+            return;
+        }
+        let profile = wallet.profile_name.clone();
+        self.scheduler.end_burst(wallet_id);
+        let standby = self.activate_standby(&profile, now);
+        info!(wallet = %wallet_id, standby = ?standby, "Wallet retired");
+        self.review.record_retired(wallet_id, standby.as_deref(), now);
+    }
+
+    /// Activate the first standby wallet left, with `profile` but a state of
+    /// its own, due at `now`.
+    ///
+    /// Returns its ID, or `None` if `retirement.replace_with_standby` is off
+    /// or no standby wallet is left.
+    fn activate_standby(&mut self, profile: &str, now: DateTime<Utc>) -> Option<String> {
+        if !self.settings.retirement.replace_with_standby || self.standby.is_empty() {
+            return None;
+        }
+        let mut config = self.standby.remove(0);
+        config.profile = profile.to_string();
+        config.standby = false;
+        // Later budget updates go by the config, so it takes the profile too
+        if let Some(configured) = self.settings.wallets.iter_mut().find(|w| w.id == config.id) {
+            configured.clone_from(&config);
+        }
+
+        let warmup = self.settings.warmup.to_settings();
+        let state = Self::wallet_from_config(&config, &warmup, self.seed, now);
+        self.scheduler
+            .seed_wallet(config.id.clone(), Self::jitter_seed(&state, self.seed));
+        self.budget
+            .set_caps(config.id.clone(), self.settings.wallet_budget(&config));
+        self.wallets.insert(config.id.clone(), state);
+        info!(wallet = %config.id, profile, "Standby wallet activated");
+        Some(config.id)
+    }
+
+    /// Run the planned batches whose review is over.
+    async fn execute_reviewed(&mut self) {
+        for batch in self.review.take_ready(self.clock.now()) {
+            info!(batch = batch.id, actions = batch.actions.len(), "Running reviewed batch");
+            for planned in &batch.actions {
+                self.execute_planned(&batch, planned).await;
+            }
+        }
+    }
+
+    /// Execute one action of a reviewed batch, unless the wallet can no longer
+    /// act.
+    #[instrument(skip_all, fields(batch = batch.id, wallet_id = %planned.wallet_id))]
+    async fn execute_planned(&mut self, batch: &PlannedBatch, planned: &PlannedAction) {
+        let wallet_id = planned.wallet_id.as_str();
+        let skip = |service: &Self, reason: &str| {
+            service
+                .review
+                .record_skipped(batch.id, wallet_id, reason, service.clock.now());
+        };
+
+        // The wallet may have been paused or tripped while under review
+        let Some(active) = self.wallets.get(wallet_id).map(|w| w.active) else {
+            return skip(self, "wallet not found");
+        };
+        if !active {
+            return skip(self, "wallet paused");
+        }
+        if self.circuit_breaker.is_tripped(wallet_id) {
+            return skip(self, "circuit breaker tripped");
+        }
+        let Some(plugin) = self
+            .engine
+            .plugins()
+            .iter()
+            .find(|plugin| plugin.id() == planned.plugin_id)
+            .cloned()
+        else {
+            return skip(self, "plugin not enabled");
+        };
+        if let Err(e) = self.refresh_wallet_state(wallet_id).await {
+            error!(error = %e, "Failed to refresh wallet state");
+            return skip(self, "wallet state refresh failed");
+        }
+        let Some(wallet) = self.wallets.get(wallet_id).cloned() else {
+            return skip(self, "wallet not found");
+        };
+
+        let action = planned.action();
+        if self.dry_run {
+            self.simulate_action(plugin.id(), &action, &wallet);
+            let now = self.clock.now();
+            self.review
+                .record_executed(batch.id, wallet_id, ActionStatus::Simulated, None, now);
+            return;
+        }
+        let execution = self.execute_action(plugin.as_ref(), &action, &wallet).await;
+        match &execution {
+            Execution::Done { result, .. } => {
+                let now = self.clock.now();
+                self.review
+                    .record_executed(batch.id, wallet_id, result.status, result.tx_hash, now);
+            }
+            Execution::OverBudget { .. } => skip(self, "budget exhausted"),
+            Execution::Aborted => skip(self, "fleet lease lost"),
+        }
+
+        // Back off if the endpoint or the budget asked us to
+        if let Some(earliest) = execution.not_before() {
+            let next = self.scheduler.delay_until(earliest);
+            if let Some(w) = self.wallets.get_mut(wallet_id) {
+                w.schedule_next(w.next_action.max(next));
+            }
+        }
+    }
+
+    /// Record a decided action as if it ran, without sending anything.
+    fn simulate_action(&mut self, plugin_id: &str, action: &Action, wallet: &WalletState) {
+        info!(action = %action.name, "DRY RUN: Would execute action");
+        // Still record for rate limiting and cooldowns in dry run
+        let now = self.clock.now();
+        self.rate_limiter.record_action(&wallet.id);
+        self.group_limiter.record_at(wallet.group.as_deref(), &wallet.id, now);
+        if let Some(w) = self.wallets.get_mut(&wallet.id) {
+            w.record_execution(action.id.clone(), now);
+            w.record_warmup_step(now);
+        }
+        self.metrics.record_result(
+            plugin_id,
+            action.id.as_str(),
+            &wallet.id,
+            &ActionResult::simulated(),
+        );
+        self.queue_follow_ups(plugin_id, &wallet.id, action, ActionStatus::Simulated);
+    }
+
+    /// Execute a decided action and record its outcome.
+    async fn execute_action(
+        &mut self,
+        plugin: &dyn ActionPlugin,
+        action: &Action,
+        wallet: &WalletState,
+    ) -> Execution {
+        if self.signers.get(&wallet.id).is_none() {
+            // Nothing to sign with: counts as a failure of the wallet
+            let error = FleetServiceError::NoSigner(wallet.id.clone());
+            error!(error = %error, "Cannot execute action");
+            let action_result = ActionResult::failure(error.to_string());
+            self.record_action_result(plugin.id(), &wallet.id, action, &action_result);
+            return Execution::Done {
+                result: action_result,
+                not_before: None,
+            };
+        }
+
+        let estimate = plugin.estimate_spend(action);
+        if !self.budget.can_spend(&wallet.id, &estimate) {
+            let resumes_at = self.budget.resumes_at(&wallet.id);
+            info!(
+                status = ?self.budget.status(&wallet.id),
+                fleet_status = ?self.budget.fleet_status(),
+                resumes_at = ?resumes_at,
+                "Budget exhausted, skipping action"
+            );
+            return Execution::OverBudget { resumes_at };
+        }
+
+        let started = Instant::now();
+        let chain = self.pool.provider_for(wallet.address);
+        let executed = self.engine.execute_action(plugin, action, wallet, &*chain).await;
+        if let Some(nonce) = self.engine.take_nonce_in_doubt(&wallet.id) {
+            self.hold_nonce(&wallet.id, nonce);
+        }
+        match executed {
+            Ok(action_result) => {
+                let action_result = match self.signers.get(&wallet.id) {
+                    Some(signer) => {
+                        self.engine
+                            .settle(plugin, action, wallet, &*chain, signer.signer(), action_result)
+                            .await
+                    }
+                    None => action_result,
+                };
+                let action_result = if action_result.duration_ms.is_some() {
+                    action_result
+                } else {
+                    action_result.with_duration(started.elapsed())
+                };
+                self.record_spend(&wallet.id, &Spend::actual(&action_result, &estimate));
+                self.record_pnl(&wallet.id, action, &action_result);
+                self.record_receipt_state(plugin, &wallet.id, &action_result);
+                self.record_action_result(plugin.id(), &wallet.id, action, &action_result);
+                Execution::Done {
+                    result: action_result,
+                    not_before: None,
+                }
+            }
+            // Not attempted, so neither a success nor a failure
+            Err(FleetError::LeaseLost) => Execution::Aborted,
+            Err(e) => {
+                let (result, not_before) = self.handle_action_error(
+                    plugin.id(),
+                    &wallet.id,
+                    action,
+                    &e,
+                    started.elapsed(),
+                );
+                Execution::Done { result, not_before }
+            }
+        }
+    }
+
+    /// Store the plugin state a mined action's receipt yielded, in place of
+    /// the next read.
+    fn record_receipt_state(
+        &mut self,
+        plugin: &dyn ActionPlugin,
+        wallet_id: &str,
+        result: &ActionResult,
+    ) {
+        let (Some(state), Some(wallet)) = (&result.plugin_state, self.wallets.get_mut(wallet_id))
+        else {
+            return;
+        };
+        let version = plugin.state_schema_version();
+        match wallet.set_raw_plugin_state(plugin.id(), state.clone(), version) {
+            Ok(()) => {
+                debug!(plugin = plugin.id(), "Updated plugin state from receipt");
+                self.receipt_states
+                    .insert((wallet_id.to_string(), plugin.id().to_string()));
+            }
+            Err(e) => warn!(plugin = plugin.id(), error = %e, "Kept newer plugin state"),
+        }
+    }
+
+    /// Record the gas a mined transaction paid, and the DATA it moved, in
+    /// the PnL ledger.
+    fn record_pnl(&mut self, wallet_id: &str, action: &Action, result: &ActionResult) {
+        let Some(tx_hash) = result.tx_hash.filter(|_| result.gas_used.is_some()) else {
+            return;
+        };
+        self.pnl.record(&PnlEntry {
+            id: tx_hash.to_string(),
+            wallet_id: wallet_id.to_string(),
+            at: self.clock.now(),
+            action_id: Some(action.id.to_string()),
+            gas_wei: result.gas_cost_wei().map_or(U256::ZERO, U256::from),
+            flows: result.data_flows.clone(),
+        });
+    }
+
+    /// Record what the plugins paid the wallet outside its own transactions
+    /// in the PnL ledger, each flow once.
+    fn record_settled_flows(&mut self, wallet: &WalletState) {
+        let now = self.clock.now();
+        for (plugin, id, flow) in self.engine.settled_flows(wallet) {
+            let recorded = self.pnl.record(&PnlEntry {
+                id: format!("{plugin}:{id}"),
+                wallet_id: wallet.id.clone(),
+                at: now,
+                action_id: None,
+                gas_wei: U256::ZERO,
+                flows: vec![flow],
+            });
+            if recorded {
+                debug!(plugin, id = %id, inflow = %flow.inflow(), "Recorded settled DATA flow");
+            }
+        }
+    }
+
+    /// Reschedule a wallet whose group has reached its action limit.
+    ///
+    /// The wallet retries shortly after the group's window frees up. Returns
+    /// `true` if the wallet was deferred.
+    fn defer_for_group_limit(&mut self, wallet_id: &str) -> bool {
+        let Some(wallet) = self.wallets.get_mut(wallet_id) else {
+            return false;
+        };
+        let Some(available_at) = self
+            .group_limiter
+            .available_at(wallet.group.as_deref(), self.clock.now())
+        else {
+            return false;
+        };
+
+        let next = self.scheduler.delay_until(available_at);
+        debug!(
+            group = ?wallet.group,
+            available_at = %available_at,
+            next = %next,
+            "Group action limit reached, rescheduling"
+        );
+        wallet.schedule_next(next);
+        true
+    }
+
+    /// Whether `action` is urgent enough to run outside active hours.
+    fn is_urgent(&self, plugin: &dyn ActionPlugin, action: &ActionId) -> bool {
+        self.trigger
+            .as_ref()
+            .is_some_and(|trigger| trigger.is_urgent(plugin, action))
+    }
+
+    /// Schedule the next action of `wallet_id` by its profile.
+    fn schedule_next_action(&mut self, wallet_id: &str, profile: &BehaviorProfile) {
+        let next = self.scheduler.calculate_next_action(wallet_id, profile);
+        if let Some(w) = self.wallets.get_mut(wallet_id) {
+            w.schedule_next(next);
+        }
+    }
+
+    /// Send `wallet_id` AFK if its profile rolls for it. Returns `true` if
+    /// the wallet went AFK.
+    fn maybe_go_afk(&mut self, wallet_id: &str, profile: &BehaviorProfile) -> bool {
+        let Some(afk_until) = self.scheduler.maybe_go_afk(profile) else {
+            return false;
+        };
+        info!(until = %afk_until, "Wallet going AFK");
+        self.scheduler.end_burst(wallet_id);
+        if let Some(w) = self.wallets.get_mut(wallet_id) {
+            w.set_afk(afk_until);
+            w.schedule_next(afk_until);
+        }
+        true
+    }
+
+    /// Reschedule a ramping wallet whose first state reads would exceed the
+    /// `[cold_start]` read rate.
+    ///
+    /// The wallet retries once a read is available. Returns `true` if the
+    /// wallet was deferred.
+    fn defer_for_ramp_reads(&mut self, wallet_id: &str) -> bool {
+        let now = self.clock.now();
+        let Some(ramp) = self
+            .cold_start
+            .as_mut()
+            .filter(|ramp| ramp.is_pending(wallet_id))
+        else {
+            return false;
+        };
+        let Some(available_at) = ramp.read_available_at(now) else {
+            return false;
+        };
+
+        debug!(available_at = %available_at, "First-cycle read rate reached, rescheduling");
+        if let Some(w) = self.wallets.get_mut(wallet_id) {
+            w.schedule_next(available_at);
+        }
+        true
+    }
+
+    /// Count a wallet's first cycle towards the cold-start ramp, logging its
+    /// progress every quarter of the way.
+    fn advance_cold_start(&mut self, wallet_id: &str) {
+        let Some(ramp) = self.cold_start.as_mut() else {
+            return;
+        };
+        let quarter = ramp.progress().percent() / 25;
+        if !ramp.start(wallet_id) {
+            return;
+        }
+        let progress = ramp.progress();
+        if progress.percent() < 100 && progress.percent() / 25 > quarter {
+            info!(
+                started = progress.started,
+                wallets = progress.wallets,
+                percent = progress.percent(),
+                "Cold-start ramp progress"
+            );
+        }
+    }
+
     /// Refresh wallet state from the chain.
     #[instrument(skip(self), fields(wallet_id = %wallet_id))]
     async fn refresh_wallet_state(&mut self, wallet_id: &str) -> Result<()> {
@@ -507,13 +2319,24 @@ impl FleetService {
             wallet.address
         };
 
+        // A wallet that moved to another endpoint keeps its nonce until the
+        // new endpoint caught up, rather than reuse nonces it already sent
+        let assigned = self.pool.assign(address);
+        let provider = assigned.endpoint;
+        if assigned.moved_from.is_some()
+            && let Some(w) = self.wallets.get(wallet_id)
+        {
+            self.nonce_floors.insert(wallet_id.to_string(), w.nonce);
+        }
+
         // Fetch native balance
-        let native_balance = self.provider.get_balance(address).await
+        let native_balance = provider.get_balance(address).await
             .context("Failed to fetch balance")?;
 
         // Fetch nonce
-        let nonce = self.provider.get_nonce(address).await
+        let nonce = provider.get_nonce(address).await
             .context("Failed to fetch nonce")?;
+        let nonce = self.resync_nonce(wallet_id, nonce);
 
         // Update wallet state
         if let Some(w) = self.wallets.get_mut(wallet_id) {
@@ -521,25 +2344,34 @@ impl FleetService {
             w.set_nonce(nonce);
         }
 
-        // Fetch DATA token balance if GHOSTNET plugin is configured
-        if let Some(ghostnet_config) = &self.settings.plugins.ghostnet {
-            let data_balance = self
-                .provider
-                .get_token_balance(ghostnet_config.data_token, address)
-                .await
-                .context("Failed to fetch token balance")?;
-
-            if let Some(w) = self.wallets.get_mut(wallet_id) {
-                w.set_token_balance(ghostnet_config.data_token, data_balance);
-            }
+        // Re-read the DATA token balance if GHOSTNET plugin is configured and
+        // the tracked one is too old. A failed read is not fatal: the balance
+        // stays stale and the plugin will not size actions from it.
+        if let Some(ghostnet_config) = &self.settings.plugins.ghostnet
+            && let Some(data_token) = self.settings.chain.ghostnet.data_token
+        {
+            let max_age = chrono::Duration::seconds(
+                i64::try_from(ghostnet_config.max_balance_age_secs).unwrap_or(i64::MAX),
+            );
+            self.refresh_token_balances(wallet_id, vec![data_token], max_age)
+                .await;
         }
 
-        // Read plugin-specific state
+        // Read plugin-specific state, unless the last receipt already said
+        // what changed
         for plugin in self.engine.plugins() {
+            let key = (wallet_id.to_string(), plugin.id().to_string());
+            if self.receipt_states.remove(&key) {
+                debug!(plugin = plugin.id(), "Plugin state is current from receipt");
+                continue;
+            }
             match plugin.read_state(address).await {
                 Ok(state) => {
-                    if let Some(w) = self.wallets.get_mut(wallet_id) {
-                        w.set_plugin_state(plugin.id(), state);
+                    let version = plugin.state_schema_version();
+                    if let Some(w) = self.wallets.get_mut(wallet_id)
+                        && let Err(e) = w.set_raw_plugin_state(plugin.id(), state, version)
+                    {
+                        warn!(plugin = plugin.id(), error = %e, "Kept newer plugin state");
                     }
                 }
                 Err(e) => {
@@ -561,6 +2393,202 @@ impl FleetService {
         Ok(())
     }
 
+    /// The nonce to use for a wallet whose endpoint reported `reported`.
+    ///
+    /// Below the wallet's floor the endpoint is still behind on transactions
+    /// sent through its previous one; once it reports the floor or more, it
+    /// is trusted again.
+    fn resync_nonce(&mut self, wallet_id: &str, reported: u64) -> u64 {
+        let Some(&floor) = self.nonce_floors.get(wallet_id) else {
+            return reported;
+        };
+        if reported >= floor {
+            self.nonce_floors.remove(wallet_id);
+            return reported;
+        }
+        debug!(reported, floor, "Endpoint is behind on the wallet's nonce, keeping it");
+        floor
+    }
+
+    /// Keep a wallet off `nonce`, which a cancelled action may have sent a
+    /// transaction with, until its endpoint reports the nonce used.
+    fn hold_nonce(&mut self, wallet_id: &str, nonce: u64) {
+        let next = nonce.saturating_add(1);
+        let floor = self.nonce_floors.entry(wallet_id.to_string()).or_default();
+        *floor = (*floor).max(next);
+        if let Some(w) = self.wallets.get_mut(wallet_id) {
+            w.set_nonce(w.nonce.max(next));
+        }
+    }
+
+    /// Re-read a wallet's token balances that are older than `max_age`.
+    async fn refresh_token_balances(
+        &mut self,
+        wallet_id: &str,
+        tokens: Vec<Address>,
+        max_age: chrono::Duration,
+    ) {
+        let Some(wallet) = self.wallets.get_mut(wallet_id) else {
+            return;
+        };
+
+        let provider = self.pool.provider_for(wallet.address);
+        let refresher = BalanceRefresher::new(provider, tokens)
+            .with_max_age(max_age)
+            .with_clock(Arc::clone(&self.clock));
+        match refresher.refresh(std::slice::from_mut(wallet)).await {
+            Ok(report) if report.failed > 0 => {
+                warn!(failed = report.failed, "Some token balances could not be refreshed");
+            }
+            Ok(_) => {}
+            Err(e) => warn!(error = %e, "Failed to refresh token balances"),
+        }
+    }
+
+    /// Record the outcome of an action in the metrics and in wallet and safety state.
+    fn record_action_result(
+        &mut self,
+        plugin_id: &str,
+        wallet_id: &str,
+        action: &fleet_core::plugins::Action,
+        result: &ActionResult,
+    ) {
+        self.metrics
+            .record_result(plugin_id, action.id.as_str(), wallet_id, result);
+        if let Some(monitor) = &mut self.degradation {
+            monitor.record_result(result);
+        }
+        self.handle_action_result(wallet_id, action, result);
+        self.queue_follow_ups(plugin_id, wallet_id, action, result.status);
+    }
+
+    /// Update wallet and safety state from the outcome of an executed action.
+    fn handle_action_result(
+        &mut self,
+        wallet_id: &str,
+        action: &fleet_core::plugins::Action,
+        result: &ActionResult,
+    ) {
+        match result.status {
+            ActionStatus::Succeeded => {
+                info!(
+                    tx_hash = ?result.tx_hash,
+                    gas_used = ?result.gas_used,
+                    block = ?result.block_number,
+                    duration_ms = ?result.duration_ms,
+                    "Action executed successfully"
+                );
+                self.circuit_breaker.record_success(wallet_id);
+                self.rate_limiter.record_action(wallet_id);
+                self.record_group_action(wallet_id);
+                let now = self.clock.now();
+                if let Some(w) = self.wallets.get_mut(wallet_id) {
+                    w.record_success_at(now);
+                    w.record_execution(action.id.clone(), now);
+                    w.record_warmup_step(now);
+                    w.increment_nonce();
+                }
+            }
+            ActionStatus::Skipped | ActionStatus::Simulated => {
+                debug!(status = %result.status, detail = %result.detail, "Action not executed");
+            }
+            ActionStatus::Reverted | ActionStatus::Dropped => {
+                warn!(
+                    status = %result.status,
+                    tx_hash = ?result.tx_hash,
+                    error = ?result.error.as_ref().map(|e| e.message.as_str()),
+                    retryable = result.is_retryable(),
+                    "Action failed"
+                );
+                self.record_wallet_error(wallet_id);
+                // A mined transaction uses up its nonce even if it reverted,
+                // and is just as visible on chain as a successful one
+                if result.status == ActionStatus::Reverted && result.tx_hash.is_some() {
+                    self.record_group_action(wallet_id);
+                    if let Some(w) = self.wallets.get_mut(wallet_id) {
+                        w.increment_nonce();
+                    }
+                } else if result.replacement.is_some_and(|r| r.outcome.used_nonce()) {
+                    // So does the transaction that cancelled it
+                    if let Some(w) = self.wallets.get_mut(wallet_id) {
+                        w.increment_nonce();
+                    }
+                }
+            }
+        }
+    }
+
+    /// Update metrics and safety state for an action that failed with an error.
+    ///
+    /// See [`FleetService`] for how each [`ErrorClass`] is handled. Returns
+    /// the recorded result, and the earliest time the wallet may act again if
+    /// it has to back off.
+    fn handle_action_error(
+        &mut self,
+        plugin_id: &str,
+        wallet_id: &str,
+        action: &Action,
+        error: &FleetError,
+        duration: Duration,
+    ) -> (ActionResult, Option<DateTime<Utc>>) {
+        let class = error.class();
+        error!(error = %error, class = %class, "Action execution error");
+
+        self.metrics.record_error(class);
+        let result = ActionResult::failure(error.to_string())
+            .with_error(ActionError {
+                message: error.to_string(),
+                retryable: class.is_retryable(),
+            })
+            .with_duration(duration);
+        self.metrics
+            .record_result(plugin_id, action.id.as_str(), wallet_id, &result);
+        if let Some(monitor) = &mut self.degradation {
+            monitor.record_result(&result);
+        }
+        self.queue_follow_ups(plugin_id, wallet_id, action, result.status);
+
+        if class == ErrorClass::RateLimited {
+            // A degraded fleet is held back already
+            if self.degradation_tier() == DegradationTier::Normal
+                && self.global_breaker.record_rate_limited()
+            {
+                warn!(wallet = %wallet_id, "Global breaker tripped, pausing all wallets");
+            }
+            let backoff = i64::try_from(self.settings.safety.rate_limit_backoff_secs)
+                .unwrap_or(i64::MAX);
+            let not_before = self
+                .clock
+                .now()
+                .checked_add_signed(chrono::Duration::seconds(backoff));
+            return (result, not_before);
+        }
+        if error.counts_toward_circuit_breaker() {
+            self.record_wallet_error(wallet_id);
+        }
+        (result, None)
+    }
+
+    /// Record what an action actually spent against the budgets and in the
+    /// wallet's ledger.
+    fn record_spend(&mut self, wallet_id: &str, spend: &Spend) {
+        if *spend == Spend::ZERO {
+            return;
+        }
+        self.budget.record_spend(wallet_id, spend);
+        if let (Some(w), Some(ledger)) =
+            (self.wallets.get_mut(wallet_id), self.budget.ledger(wallet_id))
+        {
+            w.budget.clone_from(ledger);
+        }
+    }
+
+    /// Count an on-chain action towards the wallet's group limit.
+    fn record_group_action(&mut self, wallet_id: &str) {
+        let group = self.wallets.get(wallet_id).and_then(|w| w.group.as_deref());
+        self.group_limiter.record_at(group, wallet_id, self.clock.now());
+    }
+
     /// Record an error for a wallet.
     fn record_wallet_error(&mut self, wallet_id: &str) {
         let tripped = self.circuit_breaker.record_error(wallet_id);
@@ -575,18 +2603,100 @@ impl FleetService {
 
     /// Get current wallet states (for inspection/debugging).
     #[must_use]
-    #[allow(dead_code)] // Used in tests
     pub const fn wallets(&self) -> &HashMap<String, WalletState> {
         &self.wallets
     }
 
-    /// Get the circuit breaker (for inspection/debugging).
+    /// Get the action metrics (for inspection/debugging).
     #[must_use]
     #[allow(dead_code)] // Used in tests
+    pub fn metrics(&self) -> &FleetMetrics {
+        &self.metrics
+    }
+
+    /// Snapshot of fleet-wide metrics, wallet counts, group limit usage,
+    /// endpoint traffic, cold-start ramp progress, plugin health and profit
+    /// and loss.
+    #[must_use]
+    pub fn snapshot(&self) -> FleetSnapshot {
+        let now = self.clock.now();
+        let mut snapshot = self.metrics.snapshot();
+        snapshot.fleet_id.clone_from(&self.fleet_id);
+        snapshot.timestamp = Some(now);
+        snapshot.active_wallets = self.wallets.values().filter(|w| w.is_active_at(now)).count();
+        snapshot.tripped_wallets = self.circuit_breaker.tripped_count();
+        snapshot.afk_wallets = self.wallets.values().filter(|w| w.is_afk_at(now)).count();
+        snapshot.budget_exhausted_wallets = self.budget.exhausted_count();
+        snapshot.fleet_budget_exhausted = self.budget.fleet_status().is_exhausted();
+        snapshot.group_stats = self.group_limiter.stats_at(now);
+        snapshot.endpoint_stats = self.pool.endpoint_stats();
+        snapshot.cold_start = self.cold_start.as_ref().map(ColdStartRamp::progress);
+        snapshot.plugins = self.engine.plugin_health();
+        snapshot.pnl = self.pnl.summary(now.date_naive());
+        snapshot.degradation = self
+            .degradation
+            .as_ref()
+            .map(DegradationMonitor::status)
+            .unwrap_or_default();
+        snapshot
+    }
+
+    /// What every wallet has done so far, for [activity reports](crate::report).
+    fn report_snapshot(&self) -> ReportSnapshot {
+        let now = self.clock.now();
+        let wallets = self
+            .wallets
+            .values()
+            .map(|w| {
+                let plugins = self.engine.activity(w);
+                let pnl = self.pnl.totals(&w.id);
+                let activity = Activity {
+                    actions: self.metrics.actions_for_wallet(&w.id),
+                    successes: self.metrics.successes_for_wallet(&w.id),
+                    failures: self.metrics.failures_for_wallet(&w.id),
+                    gas_wei: w.budget.lifetime.gas_wei,
+                    staked: plugins.staked,
+                    extracted: plugins.extracted,
+                    won: plugins.won,
+                    lost: plugins.lost,
+                    data_in: pnl.data_in,
+                    data_out: pnl.data_out,
+                    breaker_trips: self.circuit_breaker.trips_of(&w.id),
+                };
+                let reading = WalletReading {
+                    activity,
+                    active: w.is_active_at(now),
+                    positions: plugins.positions,
+                };
+                (w.id.clone(), reading)
+            })
+            .collect();
+        ReportSnapshot {
+            taken_at: now,
+            wallets,
+        }
+    }
+
+    /// Get the circuit breaker (for inspection/debugging).
+    #[must_use]
     pub const fn circuit_breaker(&self) -> &CircuitBreaker {
         &self.circuit_breaker
     }
 
+    /// Get the global breaker (for inspection/debugging).
+    #[must_use]
+    #[allow(dead_code)] // Used in tests
+    pub const fn global_breaker(&self) -> &GlobalBreaker {
+        &self.global_breaker
+    }
+
+    /// Get the budget manager (for inspection/debugging).
+    #[must_use]
+    #[allow(dead_code)] // Used in tests
+    pub const fn budget(&self) -> &BudgetManager {
+        &self.budget
+    }
+
     /// Check if dry run mode is enabled.
     #[must_use]
     #[allow(dead_code)] // Used in tests
@@ -594,11 +2704,11 @@ impl FleetService {
         self.dry_run
     }
 
-    /// Get a reference to the provider.
+    /// Get a reference to the RPC endpoint pool.
     #[must_use]
     #[allow(dead_code)] // Used in tests
-    pub const fn provider(&self) -> &Arc<MockProvider> {
-        &self.provider
+    pub const fn pool(&self) -> &Arc<FleetPool> {
+        &self.pool
     }
 
     /// Manually trigger a wallet reset (clears circuit breaker).
@@ -606,23 +2716,364 @@ impl FleetService {
     pub fn reset_wallet(&mut self, wallet_id: &str) {
         self.circuit_breaker.manual_reset(wallet_id);
         if let Some(w) = self.wallets.get_mut(wallet_id) {
-            w.record_success(); // Resets error count
+            w.record_success_at(self.clock.now()); // Resets error count
         }
         info!(wallet = %wallet_id, "Wallet manually reset");
     }
 
-    /// Pause all operations.
-    #[allow(dead_code)] // Used in tests and operations
-    pub fn pause(&mut self) {
-        self.settings.safety.global_pause = true;
-        info!("Service paused");
+    /// Pause all operations.
+    #[allow(dead_code)] // Used in tests and operations
+    pub fn pause(&mut self) {
+        self.settings.safety.global_pause = true;
+        info!("Service paused");
+    }
+
+    /// Resume operations.
+    #[allow(dead_code)] // Used in tests and operations
+    pub fn resume(&mut self) {
+        self.settings.safety.global_pause = false;
+        info!("Service resumed");
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════════
+    // CONTROL
+    // ═══════════════════════════════════════════════════════════════════════════
+
+    /// Handle a command from the control socket.
+    ///
+    /// Failures are answered with [`ControlResponse::Failed`] rather than
+    /// returned: a rejected command must not stop the service.
+    pub async fn handle_command(&mut self, command: ControlCommand) -> ControlResponse {
+        let response = match command {
+            ControlCommand::Status => Ok(ControlResponse::Status(Box::new(self.status()))),
+            ControlCommand::Pause { wallet_id } => self.pause_wallet(&wallet_id),
+            ControlCommand::Resume { wallet_id } => self.resume_wallet(&wallet_id),
+            ControlCommand::ResetBreaker { wallet_id: Some(wallet_id) } => {
+                self.reset_breaker(&wallet_id)
+            }
+            ControlCommand::ResetBreaker { wallet_id: None } => Ok(self.reset_all_breakers()),
+            ControlCommand::Trigger {
+                wallet_id,
+                action_id,
+            } => self
+                .trigger(&wallet_id, &ActionId::new(action_id))
+                .await
+                .map(|result| ControlResponse::Triggered(Box::new(result))),
+            ControlCommand::ReloadProfiles => self.reload_profiles(),
+            ControlCommand::Plans => Ok(ControlResponse::Plans(self.review.batches().to_vec())),
+            ControlCommand::Approve { batch_id } => self.approve_batch(batch_id),
+            ControlCommand::Reject {
+                batch_id,
+                wallet_id,
+            } => self.reject_planned(batch_id, wallet_id.as_deref()),
+            ControlCommand::Retire {
+                wallet_id,
+                sweep_to,
+            } => self.start_retiring(&wallet_id, sweep_to),
+            ControlCommand::Report { wallet_id } => self.report(wallet_id.as_deref()),
+            ControlCommand::Plugin { plugin_id, switch } => self.switch_plugin(&plugin_id, switch),
+        };
+
+        response.unwrap_or_else(|e| {
+            warn!(error = %e, "Control command failed");
+            ControlResponse::Failed(format!("{e:#}"))
+        })
+    }
+
+    /// Fleet snapshot and every wallet's schedule.
+    #[must_use]
+    pub fn status(&self) -> FleetStatus {
+        let now = self.clock.now();
+        let mut wallets: Vec<_> = self
+            .wallets
+            .values()
+            .map(|w| WalletStatus {
+                id: w.id.clone(),
+                profile: w.profile_name.clone(),
+                paused: !w.active,
+                tripped: self.circuit_breaker.is_tripped(&w.id),
+                afk_until: w.afk_until.filter(|_| w.is_afk_at(now)),
+                next_action: w.next_action,
+                consecutive_errors: w.consecutive_errors,
+                retirement: w.retirement_stage(),
+            })
+            .collect();
+        wallets.sort_unstable_by(|a, b| a.id.cmp(&b.id));
+
+        FleetStatus {
+            global_pause: self.settings.safety.global_pause,
+            global_breaker_until: self.global_breaker.open_until(),
+            snapshot: self.snapshot(),
+            wallets,
+        }
+    }
+
+    /// Activity report of the day so far, of one wallet or all of them.
+    fn report(&self, wallet_id: Option<&str>) -> Result<ControlResponse> {
+        let mut report = self.reports.so_far(&self.report_snapshot());
+        if let Some(wallet_id) = wallet_id {
+            self.wallet_state(wallet_id)?;
+            report = report.for_wallet(wallet_id);
+        }
+        Ok(ControlResponse::Report(Box::new(report)))
+    }
+
+    /// State of a wallet, or an error if the fleet has no such wallet.
+    fn wallet_state(&self, wallet_id: &str) -> Result<&WalletState> {
+        self.wallets
+            .get(wallet_id)
+            .ok_or_else(|| FleetServiceError::WalletNotFound(wallet_id.into()).into())
+    }
+
+    /// Stop scheduling a wallet until it is resumed. A burst in progress is
+    /// abandoned.
+    fn pause_wallet(&mut self, wallet_id: &str) -> Result<ControlResponse> {
+        self.wallet_state(wallet_id)?;
+        self.scheduler.end_burst(wallet_id);
+        if let Some(w) = self.wallets.get_mut(wallet_id) {
+            w.active = false;
+        }
+        info!(wallet = %wallet_id, "Wallet paused");
+        Ok(ControlResponse::Done(format!("Paused wallet {wallet_id}")))
+    }
+
+    /// Disable or enable a plugin by hand, or return it to its automatic
+    /// health. Wallets fall through to the other plugins while it is
+    /// disabled.
+    fn switch_plugin(&mut self, plugin_id: &str, switch: PluginSwitch) -> Result<ControlResponse> {
+        if !self.engine.set_plugin_override(plugin_id, switch.to_override()) {
+            return Err(
+                FleetServiceError::Control(format!("Plugin {plugin_id} is not enabled")).into(),
+            );
+        }
+        info!(plugin = %plugin_id, ?switch, "Plugin switched");
+        Ok(ControlResponse::Done(match switch {
+            PluginSwitch::Enable => format!("Enabled plugin {plugin_id}"),
+            PluginSwitch::Disable => format!("Disabled plugin {plugin_id}"),
+            PluginSwitch::Auto => format!("Plugin {plugin_id} follows its error rate again"),
+        }))
+    }
+
+    /// Schedule a paused wallet again. If it became due while paused, it acts
+    /// on the next tick.
+    fn resume_wallet(&mut self, wallet_id: &str) -> Result<ControlResponse> {
+        if self.wallet_state(wallet_id)?.is_retired() {
+            return Err(FleetServiceError::Control(format!("Wallet {wallet_id} is retired")).into());
+        }
+        if let Some(w) = self.wallets.get_mut(wallet_id) {
+            w.active = true;
+        }
+        info!(wallet = %wallet_id, "Wallet resumed");
+        Ok(ControlResponse::Done(format!("Resumed wallet {wallet_id}")))
+    }
+
+    /// Start winding a wallet down, sweeping to `sweep_to` or else to its
+    /// configured `sweep_to`. A retiring wallet takes the new sweep address.
+    fn start_retiring(
+        &mut self,
+        wallet_id: &str,
+        sweep_to: Option<Address>,
+    ) -> Result<ControlResponse> {
+        let wallet = self.wallet_state(wallet_id)?;
+        let rejected = |reason: String| Err(FleetServiceError::Control(reason).into());
+        if wallet.is_retired() {
+            return rejected(format!("Wallet {wallet_id} is already retired"));
+        }
+        if sweep_to == Some(wallet.address) {
+            return rejected(format!("Wallet {wallet_id} cannot sweep to itself"));
+        }
+        let sweep_to = sweep_to.or_else(|| {
+            self.settings
+                .wallets
+                .iter()
+                .find(|w| w.id == wallet_id)
+                .and_then(|w| w.sweep_to)
+        });
+
+        let now = self.clock.now();
+        if let Some(w) = self.wallets.get_mut(wallet_id) {
+            w.start_retiring(sweep_to, now);
+            w.warmup = None;
+        }
+        info!(wallet = %wallet_id, sweep_to = ?sweep_to, "Wallet retiring");
+        Ok(ControlResponse::Done(format!("Retiring wallet {wallet_id}")))
+    }
+
+    /// Reset the circuit breaker of a wallet.
+    fn reset_breaker(&mut self, wallet_id: &str) -> Result<ControlResponse> {
+        self.wallet_state(wallet_id)?;
+        self.reset_wallet(wallet_id);
+        Ok(ControlResponse::Done(format!("Reset circuit breaker of {wallet_id}")))
+    }
+
+    /// Reset every tripped circuit breaker, and the global breaker.
+    fn reset_all_breakers(&mut self) -> ControlResponse {
+        let tripped: Vec<_> = self
+            .circuit_breaker
+            .tripped_wallets()
+            .map(str::to_string)
+            .collect();
+        for wallet_id in &tripped {
+            self.reset_wallet(wallet_id);
+        }
+        self.circuit_breaker.reset_all();
+        self.global_breaker.reset();
+        ControlResponse::Done(format!(
+            "Reset {} circuit breakers and the global breaker",
+            tripped.len()
+        ))
+    }
+
+    /// Run one action of a wallet now, whatever its schedule.
+    ///
+    /// The action is rejected if the wallet is paused, its circuit breaker or
+    /// the global breaker is open, or the rate limit or budget would be
+    /// exceeded. Cooldowns are not enforced, and the wallet's schedule is
+    /// left as it is.
+    async fn trigger(&mut self, wallet_id: &str, action_id: &ActionId) -> Result<ActionResult> {
+        let wallet = self.wallet_state(wallet_id)?;
+        let rejected = |reason: String| Err(FleetServiceError::Control(reason).into());
+        if wallet.is_retired() {
+            return rejected(format!("Wallet {wallet_id} is retired"));
+        }
+        if !wallet.active {
+            return rejected(format!("Wallet {wallet_id} is paused"));
+        }
+        if let Some(until) = self.global_breaker.open_until() {
+            return rejected(format!("Global breaker is open until {until}"));
+        }
+        let tier = self.degradation_tier();
+        if tier != DegradationTier::Normal {
+            return rejected(format!("RPC degraded, fleet is in {tier}"));
+        }
+        if self.circuit_breaker.is_tripped(wallet_id) {
+            return rejected(format!("Circuit breaker of {wallet_id} is tripped"));
+        }
+        if self.rate_limiter.would_exceed(wallet_id) {
+            return rejected(format!("Rate limit of {wallet_id} reached"));
+        }
+
+        let profile = self.profile_of(wallet)?;
+        self.refresh_wallet_state(wallet_id).await?;
+        let wallet = self.wallet_state(wallet_id)?.clone();
+
+        let Some((plugin, action)) = self.engine.decide_forced(&wallet, &profile, action_id).await
+        else {
+            return rejected(format!("No enabled plugin offers action {action_id}"));
+        };
+        if wallet.is_retiring() && !plugin.is_exit_action(action_id) {
+            return rejected(format!("Wallet {wallet_id} is retiring and only exits"));
+        }
+        info!(wallet = %wallet_id, action = %action.name, plugin = plugin.id(), "Action triggered");
+
+        if self.dry_run {
+            self.simulate_action(plugin.id(), &action, &wallet);
+            return Ok(ActionResult::simulated());
+        }
+        match self.execute_action(plugin.as_ref(), &action, &wallet).await {
+            Execution::Done { result, .. } => Ok(result),
+            Execution::OverBudget { resumes_at } => rejected(resumes_at.map_or_else(
+                || format!("Budget of {wallet_id} is exhausted"),
+                |at| format!("Budget of {wallet_id} is exhausted until {at}"),
+            )),
+            Execution::Aborted => rejected("Fleet lease lost before sending".to_string()),
+        }
+    }
+
+    /// Run a planned batch on the next tick.
+    fn approve_batch(&mut self, batch_id: u64) -> Result<ControlResponse> {
+        let batch = self.review.approve(batch_id, self.clock.now())?;
+        Ok(ControlResponse::Done(format!(
+            "Approved batch {batch_id} of {} actions",
+            batch.accepted().count()
+        )))
+    }
+
+    /// Drop one wallet's action from a planned batch, or the whole batch.
+    ///
+    /// Rejected actions are never attempted: they leave the wallets' errors,
+    /// breakers and metrics as they are.
+    fn reject_planned(
+        &mut self,
+        batch_id: u64,
+        wallet_id: Option<&str>,
+    ) -> Result<ControlResponse> {
+        let rejected = self.review.reject(batch_id, wallet_id, self.clock.now())?;
+        Ok(ControlResponse::Done(format!(
+            "Rejected {rejected} actions of batch {batch_id}"
+        )))
+    }
+
+    /// Re-read `[profiles]` from the config file.
+    ///
+    /// The new profiles are validated against the rest of the running
+    /// configuration and replace the old ones only if valid. Budget caps that
+    /// wallets inherit from their profile are updated as well.
+    fn reload_profiles(&mut self) -> Result<ControlResponse> {
+        let path = self
+            .config_path
+            .as_ref()
+            .context("No config file to reload profiles from")?;
+        let loaded = Settings::load(path)?;
+
+        let mut settings = self.settings.clone();
+        settings.profiles = loaded.profiles;
+        settings.validate().context("Reloaded profiles are invalid")?;
+
+        self.profiles = settings.profile_catalog()?;
+        self.composition = Self::compose_profiles(&settings, &self.profiles, &self.wallets);
+        for wallet in &settings.wallets {
+            self.budget
+                .set_caps(wallet.id.clone(), settings.wallet_budget(wallet));
+        }
+        self.settings = settings;
+
+        info!(profiles = self.profiles.len(), path = %path.display(), "Profiles reloaded");
+        Ok(ControlResponse::Done(format!(
+            "Reloaded {} profiles",
+            self.profiles.len()
+        )))
+    }
+}
+
+/// Replacement of stuck transactions as configured in `[safety]`.
+fn replacement_policy(safety: &SafetyConfig) -> ReplacementPolicy {
+    ReplacementPolicy {
+        receipt_timeout: Duration::from_secs(safety.receipt_timeout_secs),
+        action_timeouts: safety
+            .action_receipt_timeout_secs
+            .iter()
+            .map(|(action, secs)| (ActionId::new(action.as_str()), Duration::from_secs(*secs)))
+            .collect(),
+        max_replacements: safety.max_replacements,
+        fee_bump_pct: safety.replacement_fee_bump_pct,
+    }
+}
+
+/// Timeouts of plugin calls as configured in `[safety]`.
+fn action_timeouts(safety: &SafetyConfig) -> ActionTimeouts {
+    let overrides = |secs: fn(&PluginTimeoutConfig) -> Option<u64>| -> HashMap<_, _> {
+        safety
+            .plugin_timeouts
+            .iter()
+            .filter_map(|(plugin, timeouts)| {
+                Some((plugin.clone(), Duration::from_secs(secs(timeouts)?)))
+            })
+            .collect()
+    };
+    ActionTimeouts {
+        decide: Duration::from_secs(safety.decide_timeout_secs),
+        execute: Duration::from_secs(safety.execute_timeout_secs),
+        plugin_decide: overrides(|timeouts| timeouts.decide_secs),
+        plugin_execute: overrides(|timeouts| timeouts.execute_secs),
     }
+}
 
-    /// Resume operations.
-    #[allow(dead_code)] // Used in tests and operations
-    pub fn resume(&mut self) {
-        self.settings.safety.global_pause = false;
-        info!("Service resumed");
+/// Next command from the control socket; never resolves without one.
+async fn next_command(control: &mut Option<mpsc::Receiver<Envelope>>) -> Option<Envelope> {
+    match control {
+        Some(receiver) => receiver.recv().await,
+        None => std::future::pending().await,
     }
 }
 
@@ -631,9 +3082,18 @@ impl FleetService {
 // ═══════════════════════════════════════════════════════════════════════════════
 
 #[cfg(test)]
+#[allow(clippy::unwrap_used)]
 mod tests {
+    use evm_provider::pool::AssignmentStrategy;
+    use fleet_core::clock::{Clock, VirtualClock};
+    use fleet_core::plugins::{FollowUpAction, FollowUpCondition, ReplacementOutcome};
+    use fleet_core::validation::ConfigReport;
+
     use super::*;
-    use crate::config::{ChainConfig, PluginsConfig, ProfileConfig, SafetyConfig, ServiceConfig};
+    use crate::config::{
+        BudgetConfig, ChainConfig, GroupConfig, PluginsConfig, ProfileConfig, SafetyConfig,
+        ServiceConfig, WalletConfig,
+    };
 
     fn test_settings() -> Settings {
         let mut profiles = HashMap::new();
@@ -645,20 +3105,36 @@ mod tests {
                 chain_id: 31337,
                 rpc_url: "http://localhost:8545".to_string(),
                 chain_type: "mock".to_string(),
-                gas_limit_override: None,
-                use_realtime: false,
+                ..ChainConfig::default()
             },
+            chains: HashMap::new(),
+            selected_chain: None,
             wallets: vec![],
             plugins: PluginsConfig::default(),
             safety: SafetyConfig::default(),
             profiles,
+            groups: HashMap::new(),
+            mnemonics: HashMap::new(),
+            simulation: crate::config::SimulationConfig::default(),
+            control: crate::config::ControlConfig::default(),
+            leader: crate::config::LeaderConfig::default(),
+            warmup: crate::config::WarmupConfig::default(),
+            cold_start: crate::config::ColdStartConfig::default(),
+            diversity: crate::config::DiversityConfig::default(),
+            review: crate::config::ReviewConfig::default(),
+            retirement: crate::config::RetirementConfig::default(),
+            report: crate::config::ReportConfig::default(),
+            triggers: crate::config::TriggersConfig::default(),
+            degradation: crate::config::DegradationConfig::default(),
+            fleets: std::collections::BTreeMap::new(),
+            load_issues: ConfigReport::new(),
         }
     }
 
     #[tokio::test]
     async fn service_initializes() {
         let settings = test_settings();
-        let service = FleetService::new(settings, true).await;
+        let service = FleetService::new(settings, true, Keyring::new()).await;
         assert!(service.is_ok());
 
         let service = service.unwrap();
@@ -669,7 +3145,7 @@ mod tests {
     #[tokio::test]
     async fn pause_and_resume() {
         let settings = test_settings();
-        let mut service = FleetService::new(settings, true).await.unwrap();
+        let mut service = FleetService::new(settings, true, Keyring::new()).await.unwrap();
 
         assert!(!service.settings.safety.global_pause);
 
@@ -683,7 +3159,7 @@ mod tests {
     #[tokio::test]
     async fn circuit_breaker_integration() {
         let settings = test_settings();
-        let mut service = FleetService::new(settings, true).await.unwrap();
+        let mut service = FleetService::new(settings, true, Keyring::new()).await.unwrap();
 
         // Record errors until circuit trips
         for _ in 0..5 {
@@ -697,16 +3173,346 @@ mod tests {
         assert!(!service.circuit_breaker.is_tripped("test_wallet"));
     }
 
+    #[tokio::test]
+    async fn reverted_actions_use_up_nonce() {
+        let settings = test_settings();
+        let mut service = FleetService::new(settings, false, Keyring::new()).await.unwrap();
+        service
+            .wallets
+            .insert("w".into(), WalletState::new("w".into(), Address::ZERO));
+        let action = fleet_core::plugins::Action::new("test.act", "Act");
+
+        let reverted = ActionResult::reverted(alloy::primitives::TxHash::ZERO, "out of gas");
+        service.handle_action_result("w", &action, &reverted);
+        service.handle_action_result("w", &action, &ActionResult::failure("not sent"));
+        service.handle_action_result("w", &action, &ActionResult::skipped("nothing to do"));
+
+        // A cancelled transaction uses up the nonce too, an abandoned one not
+        let stuck = ActionResult::dropped(alloy::primitives::TxHash::ZERO, "not mined");
+        let cancelled = stuck.clone().with_replacement(ReplacementOutcome::Cancelled, 1);
+        let abandoned = stuck.with_replacement(ReplacementOutcome::Abandoned, 3);
+        service.handle_action_result("w", &action, &cancelled);
+        service.handle_action_result("w", &action, &abandoned);
+
+        let wallet = &service.wallets()["w"];
+        assert_eq!(wallet.nonce, 2);
+        assert_eq!(wallet.consecutive_errors, 4);
+        assert!(wallet.last_executed.is_empty());
+    }
+
+    #[tokio::test]
+    async fn nonce_survives_endpoint_switch() {
+        let primary = Arc::new(MockProvider::with_chain_id(31337));
+        let backup = Arc::new(MockProvider::with_chain_id(31337));
+        let pool = ProviderPool::new(
+            AssignmentStrategy::RoundRobin,
+            vec![
+                ("primary".into(), 1, Arc::clone(&primary)),
+                ("backup".into(), 1, Arc::clone(&backup)),
+            ],
+        )
+        .unwrap()
+        .with_failure_threshold(1)
+        .with_retry_after(Duration::from_secs(3600));
+        let runtime = Runtime {
+            pool: Arc::new(pool),
+            registry: PluginRegistry::new(),
+            clock: system_clock(),
+            seed: Some(1),
+            signers: Keyring::new(),
+        };
+        let mut service = FleetService::with_runtime(test_settings(), true, runtime);
+        let address = Address::repeat_byte(1);
+        service
+            .wallets
+            .insert("w".into(), WalletState::new("w".into(), address));
+
+        // The backup endpoint lags behind the primary
+        primary.set_nonce(address, 5);
+        backup.set_nonce(address, 3);
+        service.refresh_wallet_state("w").await.unwrap();
+        assert_eq!(service.wallets()["w"].nonce, 5);
+        service.wallets.get_mut("w").unwrap().increment_nonce();
+
+        // The primary goes down and the wallet moves, without going back to
+        // nonces it already used
+        primary.set_offline(true);
+        assert!(service.refresh_wallet_state("w").await.is_err());
+        service.refresh_wallet_state("w").await.unwrap();
+        assert_eq!(service.pool().assigned_endpoint(address), Some("backup"));
+        assert_eq!(service.wallets()["w"].nonce, 6);
+
+        // Once the backup caught up it is trusted again
+        backup.set_nonce(address, 7);
+        service.refresh_wallet_state("w").await.unwrap();
+        assert_eq!(service.wallets()["w"].nonce, 7);
+        assert!(service.nonce_floors.is_empty());
+
+        let stats = service.snapshot().endpoint_stats;
+        assert_eq!((stats[0].wallets, stats[1].wallets), (0, 1));
+        assert_eq!((stats[0].requests, stats[0].failures), (3, 1));
+        assert_eq!(stats[1].requests, 4);
+    }
+
+    fn fail(service: &mut FleetService, class: ErrorClass) -> Option<DateTime<Utc>> {
+        let action = Action::new("test.act", "Act");
+        let error = FleetError::plugin(class, "boom");
+        service
+            .handle_action_error("test", "w", &action, &error, Duration::ZERO)
+            .1
+    }
+
+    #[tokio::test]
+    async fn execution_errors_are_handled_by_class() {
+        let settings = test_settings();
+        let mut service = FleetService::new(settings, false, Keyring::new()).await.unwrap();
+        service
+            .wallets
+            .insert("w".into(), WalletState::new("w".into(), Address::ZERO));
+
+        // Transient errors reach the service only once retries are used up
+        assert_eq!(fail(&mut service, ErrorClass::Transient), None);
+        assert_eq!(service.circuit_breaker.error_count("w"), 1);
+        assert_eq!(fail(&mut service, ErrorClass::Permanent), None);
+        assert_eq!(service.circuit_breaker.error_count("w"), 2);
+
+        // Configuration errors need an operator, not a breaker
+        assert_eq!(fail(&mut service, ErrorClass::Configuration), None);
+        assert_eq!(service.circuit_breaker.error_count("w"), 2);
+
+        // Rate limits back the wallet off and feed the global breaker instead
+        let before = Utc::now();
+        let not_before = fail(&mut service, ErrorClass::RateLimited).unwrap();
+        assert!(not_before >= before + chrono::Duration::seconds(300));
+        assert_eq!(service.circuit_breaker.error_count("w"), 2);
+        assert_eq!(service.wallets()["w"].consecutive_errors, 2);
+        assert_eq!(service.global_breaker().recent_hits(), 1);
+        assert!(!service.global_breaker().is_open());
+
+        for class in ErrorClass::ALL {
+            assert_eq!(service.metrics().errors_with_class(class), 1, "{class}");
+        }
+        assert_eq!(service.metrics().failed_actions(), 4);
+    }
+
+    #[tokio::test]
+    async fn rate_limits_trip_global_breaker() {
+        let mut settings = test_settings();
+        settings.safety.max_rate_limited_errors = 2;
+        let mut service = FleetService::new(settings, false, Keyring::new()).await.unwrap();
+        service
+            .wallets
+            .insert("w".into(), WalletState::new("w".into(), Address::ZERO));
+
+        fail(&mut service, ErrorClass::RateLimited);
+        fail(&mut service, ErrorClass::RateLimited);
+
+        let until = service.global_breaker().open_until().unwrap();
+        assert_eq!(service.global_breaker().total_trips(), 1);
+        assert_eq!(service.circuit_breaker.error_count("w"), 0);
+        // The wallet is due, but nobody acts before the breaker closes
+        assert_eq!(service.next_wakeup(), Some(until));
+    }
+
+    #[tokio::test]
+    async fn budget_exhaustion_shows_in_snapshot() {
+        let mut settings = test_settings();
+        settings.safety.budget.daily_actions = Some(3);
+        settings.profiles.get_mut("test_profile").unwrap().budget.daily_actions = Some(2);
+        settings.wallets = ["a", "b"]
+            .into_iter()
+            .map(|id| WalletConfig {
+                id: id.into(),
+                address: Address::ZERO,
+                profile: "test_profile".into(),
+                key_source: None,
+                private_key: None,
+                enabled: true,
+                group: None,
+                retiring: false,
+                sweep_to: None,
+                standby: false,
+                budget: BudgetConfig {
+                    daily_actions: (id == "a").then_some(1),
+                    ..BudgetConfig::default()
+                },
+            })
+            .collect();
+        let mut service = FleetService::new(settings, false, Keyring::new()).await.unwrap();
+
+        service.record_spend("a", &Spend::action());
+        assert!(!service.budget().can_spend("a", &Spend::action()));
+        assert!(service.budget().can_spend("b", &Spend::action()));
+        assert_eq!(service.wallets()["a"].budget.daily.actions, 1);
+
+        // Exhausted is not tripped
+        let snapshot = service.snapshot();
+        assert_eq!(snapshot.budget_exhausted_wallets, 1);
+        assert_eq!(snapshot.tripped_wallets, 0);
+        assert!(!snapshot.fleet_budget_exhausted);
+
+        // b falls back to the profile's cap, and takes the fleet to its own
+        service.record_spend("b", &Spend::action());
+        service.record_spend("b", &Spend::action());
+        let snapshot = service.snapshot();
+        assert_eq!(snapshot.budget_exhausted_wallets, 2);
+        assert!(snapshot.fleet_budget_exhausted);
+    }
+
+    #[tokio::test]
+    async fn wallets_keep_their_personality_across_restarts() {
+        let mut settings = test_settings();
+        settings.wallets = (1..=2)
+            .map(|byte| WalletConfig {
+                id: format!("w{byte}"),
+                address: Address::repeat_byte(byte),
+                profile: "test_profile".into(),
+                key_source: None,
+                private_key: None,
+                enabled: true,
+                group: None,
+                retiring: false,
+                sweep_to: None,
+                standby: false,
+                budget: BudgetConfig::default(),
+            })
+            .collect();
+        let mut first = FleetService::new(settings.clone(), true, Keyring::new()).await.unwrap();
+        let mut restarted = FleetService::new(settings, true, Keyring::new()).await.unwrap();
+
+        let profile = |service: &FleetService, id: &str| {
+            service.profile_of(&service.wallets()[id]).unwrap()
+        };
+        assert_eq!(profile(&first, "w1"), profile(&restarted, "w1"));
+        assert_ne!(profile(&first, "w1"), profile(&first, "w2"));
+
+        // Unseeded services still replay each wallet's jitter
+        let w1 = profile(&first, "w1");
+        for _ in 0..5 {
+            let a = first.scheduler.calculate_next_action("w1", &w1);
+            let b = restarted.scheduler.calculate_next_action("w1", &w1);
+            assert!((a - b).num_seconds().abs() <= 1);
+        }
+    }
+
+    #[tokio::test]
+    async fn wallet_profiles_are_composed_for_diversity() {
+        let mut settings = test_settings();
+        settings.diversity.min_hourly_coverage = 0.25;
+        settings.diversity.risk_buckets = vec![1.0, 1.0];
+        settings.wallets = (1..=8)
+            .map(|byte| WalletConfig {
+                id: format!("w{byte}"),
+                address: Address::repeat_byte(byte),
+                profile: "test_profile".into(),
+                key_source: None,
+                private_key: None,
+                enabled: true,
+                group: None,
+                retiring: false,
+                sweep_to: None,
+                standby: false,
+                budget: BudgetConfig::default(),
+            })
+            .collect();
+        let service = FleetService::new(settings.clone(), true, Keyring::new()).await.unwrap();
+        let restarted = FleetService::new(settings, true, Keyring::new()).await.unwrap();
+
+        let report = service.composition.report();
+        assert!(report.is_satisfied(), "{report:?}");
+        assert_eq!(report, restarted.composition.report());
+
+        let profiles: Vec<_> = (1..=8)
+            .map(|i| service.profile_of(&service.wallets()[&format!("w{i}")]).unwrap())
+            .collect();
+        for hour in 0..24 {
+            let active = profiles.iter().filter(|p| p.is_active_hour(hour)).count();
+            assert!(active >= 2, "{active} wallets active at {hour}:00");
+        }
+        assert_eq!(profiles.iter().filter(|p| p.risk_tolerance < 0.5).count(), 4);
+    }
+
+    #[test]
+    fn new_wallets_warm_up_when_enabled() {
+        let mut settings = test_settings();
+        settings.wallets = (1..=2)
+            .map(|byte| WalletConfig {
+                id: format!("w{byte}"),
+                address: Address::repeat_byte(byte),
+                profile: "test_profile".into(),
+                key_source: None,
+                private_key: None,
+                enabled: true,
+                group: None,
+                retiring: false,
+                sweep_to: None,
+                standby: false,
+                budget: BudgetConfig::default(),
+            })
+            .collect();
+        let now = Utc::now();
+
+        let wallets = FleetService::initialize_wallets(&settings, now, None);
+        assert!(wallets.values().all(|w| w.warmup.is_none()));
+
+        settings.warmup.enabled = true;
+        let wallets = FleetService::initialize_wallets(&settings, now, Some(7));
+        let plan = |id: &str| wallets[id].warmup_at(now).cloned().unwrap();
+        assert_ne!(plan("w1"), plan("w2"));
+        assert_eq!(
+            FleetService::initialize_wallets(&settings, now, Some(7))["w1"].warmup,
+            Some(plan("w1"))
+        );
+    }
+
+    #[tokio::test]
+    async fn group_limit_reschedules_wallet() {
+        let mut settings = test_settings();
+        settings.groups.insert(
+            "funded_by_a".into(),
+            GroupConfig {
+                max_actions: 1,
+                window_secs: 600,
+            },
+        );
+        let mut service = FleetService::new(settings, true, Keyring::new()).await.unwrap();
+        for id in ["w1", "w2", "w3"] {
+            let mut wallet =
+                WalletState::with_profile(id.into(), Address::ZERO, "test_profile".into());
+            wallet.group = (id != "w3").then(|| "funded_by_a".into());
+            service.wallets.insert(id.into(), wallet);
+        }
+        service.record_group_action("w1");
+
+        let before = Utc::now();
+        service.process_wallet("w2").await.unwrap();
+
+        // w2 waits until w1's action leaves the window, plus jitter
+        let next = service.wallets()["w2"].next_action;
+        assert!(next > before + chrono::Duration::minutes(10));
+        let latest = Utc::now() + chrono::Duration::minutes(10) + chrono::Duration::seconds(30);
+        assert!(next <= latest);
+        assert_eq!(service.metrics().total_actions(), 0);
+
+        // Wallets outside the group are not held back
+        let w3_group = service.wallets()["w3"].group.as_deref();
+        assert!(service.group_limiter.is_allowed(w3_group));
+        let snapshot = service.snapshot();
+        assert_eq!(snapshot.group_stats.len(), 1);
+        assert_eq!(snapshot.group_stats[0].actions_in_window, 1);
+        assert_eq!(snapshot.active_wallets, 3);
+    }
+
     #[tokio::test]
     async fn shutdown_signal_stops_service() {
         let settings = test_settings();
-        let service = FleetService::new(settings, true).await.unwrap();
+        let service = FleetService::new(settings, true, Keyring::new()).await.unwrap();
 
         let (shutdown_tx, shutdown_rx) = watch::channel(false);
 
         // Spawn service in a task
         let handle = tokio::spawn(async move {
-            service.run(shutdown_rx).await
+            Box::pin(service.run(shutdown_rx)).await
         });
 
         // Give it a moment to start
@@ -751,4 +3557,673 @@ mod tests {
         limiter.record_action("wallet_2");
         assert!(!limiter.would_exceed("wallet_2"));
     }
+
+    /// Plugin deciding one action with the follow-ups it was given, then
+    /// nothing, and recording what it runs; listed actions revert.
+    #[derive(Debug, Default)]
+    struct ChainPlugin {
+        decided: std::sync::Mutex<Option<Action>>,
+        executed: std::sync::Mutex<Vec<String>>,
+        reverting: Vec<&'static str>,
+    }
+
+    impl ChainPlugin {
+        fn deciding(action: Action) -> Self {
+            Self {
+                decided: std::sync::Mutex::new(Some(action)),
+                ..Self::default()
+            }
+        }
+
+        fn executed(&self) -> Vec<String> {
+            self.executed.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl ActionPlugin for ChainPlugin {
+        fn id(&self) -> &'static str {
+            "chain"
+        }
+
+        fn name(&self) -> &'static str {
+            "Chain"
+        }
+
+        fn available_actions(&self) -> Vec<ActionId> {
+            ["chain.approve", "chain.stake", "chain.boost", "chain.retry"]
+                .into_iter()
+                .map(ActionId::new)
+                .collect()
+        }
+
+        async fn decide_action(
+            &self,
+            _wallet: &WalletState,
+            _profile: &BehaviorProfile,
+            _context: &mut fleet_core::plugins::PluginContext<'_>,
+        ) -> fleet_core::Result<Option<Action>> {
+            Ok(self.decided.lock().unwrap().take())
+        }
+
+        async fn execute_action(
+            &self,
+            action: &Action,
+            _wallet: &WalletState,
+            _nonce: u64,
+        ) -> fleet_core::Result<ActionResult> {
+            self.executed.lock().unwrap().push(action.id.to_string());
+            if self.reverting.contains(&action.id.as_str()) {
+                return Ok(ActionResult::reverted(alloy::primitives::TxHash::ZERO, "nope"));
+            }
+            Ok(ActionResult::success(alloy::primitives::TxHash::ZERO))
+        }
+
+        async fn read_state(&self, _address: Address) -> fleet_core::Result<serde_json::Value> {
+            Ok(serde_json::Value::Null)
+        }
+    }
+
+    /// A wallet acting every few hours, reached at noon on a virtual clock.
+    fn chain_service(plugin: ChainPlugin) -> (FleetService, Arc<ChainPlugin>, Arc<VirtualClock>) {
+        use chrono::TimeZone;
+
+        let settings: Settings = toml::from_str(
+            r#"
+            [chain]
+            chain_id = 31337
+            rpc_url = "http://localhost:8545"
+            chain_type = "mock"
+
+            [plugins]
+            enabled = ["chain"]
+
+            [[wallets]]
+            id = "w1"
+            address = "0x0000000000000000000000000000000000000001"
+            profile = "steady"
+
+            [profiles.steady]
+            afk_probability = 0.0
+            action_interval_secs = 14400
+            "#,
+        )
+        .unwrap();
+
+        let clock = Arc::new(VirtualClock::new(
+            Utc.with_ymd_and_hms(2025, 1, 1, 12, 0, 0).unwrap(),
+        ));
+        let plugin = Arc::new(plugin);
+        let mut registry = PluginRegistry::new();
+        registry.register(Arc::clone(&plugin) as Arc<dyn ActionPlugin>).unwrap();
+        let runtime = Runtime {
+            pool: Arc::new(ProviderPool::single(
+                "primary",
+                Arc::new(MockProvider::with_chain_id(31337)),
+            )),
+            registry,
+            clock: Arc::clone(&clock) as _,
+            seed: Some(7),
+            signers: Keyring::development(["w1"]).unwrap(),
+        };
+        (FleetService::with_runtime(settings, false, runtime), plugin, clock)
+    }
+
+    /// Run ticks until the wallet's next action is more than an hour away.
+    async fn run_chain(service: &mut FleetService, clock: &VirtualClock) {
+        let start = clock.now();
+        loop {
+            service.process_tick().await;
+            let next = service.wallets()["w1"].next_action;
+            if next - start > chrono::Duration::hours(1) {
+                break;
+            }
+            clock.set(next.max(clock.now()));
+        }
+    }
+
+    fn stake_after_approve() -> Action {
+        Action::new("chain.approve", "Approve").with_follow_up(
+            FollowUpAction::new("chain.stake", "Stake")
+                .with_delay_secs(30, 60)
+                .with_follow_up(
+                    FollowUpAction::new("chain.boost", "Boost").with_delay_secs(30, 60),
+                ),
+        )
+    }
+
+    #[tokio::test]
+    async fn follow_ups_run_in_order_ahead_of_schedule() {
+        let plugin = ChainPlugin::deciding(stake_after_approve());
+        let (mut service, plugin, clock) = chain_service(plugin);
+        let start = clock.now();
+
+        service.process_tick().await;
+        assert_eq!(plugin.executed(), ["chain.approve"]);
+        // The stake is due within a minute, not in four hours
+        let next = service.wallets()["w1"].next_action;
+        assert!(next - start <= chrono::Duration::seconds(60), "{next}");
+
+        run_chain(&mut service, &clock).await;
+        assert_eq!(plugin.executed(), ["chain.approve", "chain.stake", "chain.boost"]);
+        assert!(clock.now() - start <= chrono::Duration::minutes(2));
+        assert!(service.wallets()["w1"].follow_ups.is_empty());
+    }
+
+    #[tokio::test]
+    async fn follow_up_chains_stop_at_the_depth_cap() {
+        let mut chain = FollowUpAction::new("chain.retry", "Retry");
+        for _ in 0..MAX_CHAIN_DEPTH + 2 {
+            chain = FollowUpAction::new("chain.retry", "Retry").with_follow_up(chain);
+        }
+        let decided = Action::new("chain.approve", "Approve").with_follow_up(chain);
+        let (mut service, plugin, clock) = chain_service(ChainPlugin::deciding(decided));
+
+        run_chain(&mut service, &clock).await;
+        let depth = usize::try_from(MAX_CHAIN_DEPTH).unwrap();
+        assert_eq!(plugin.executed().len(), 1 + depth);
+        assert!(service.wallets()["w1"].follow_ups.is_empty());
+    }
+
+    #[tokio::test]
+    async fn follow_ups_run_only_after_matching_outcomes() {
+        let decided = Action::new("chain.approve", "Approve")
+            .with_follow_up(FollowUpAction::new("chain.stake", "Stake"))
+            .with_follow_up(
+                FollowUpAction::new("chain.retry", "Retry")
+                    .with_condition(FollowUpCondition::OnFailure),
+            )
+            .with_follow_up(
+                FollowUpAction::new("chain.boost", "Boost")
+                    .with_condition(FollowUpCondition::Always),
+            );
+        let plugin = ChainPlugin {
+            reverting: vec!["chain.approve"],
+            ..ChainPlugin::deciding(decided)
+        };
+        let (mut service, plugin, clock) = chain_service(plugin);
+
+        run_chain(&mut service, &clock).await;
+        assert_eq!(plugin.executed(), ["chain.approve", "chain.retry", "chain.boost"]);
+    }
+
+    #[tokio::test]
+    async fn pending_follow_ups_survive_restart() {
+        let plugin = ChainPlugin::deciding(stake_after_approve());
+        let (mut first, plugin, clock) = chain_service(plugin);
+        first.process_tick().await;
+        assert_eq!(plugin.executed(), ["chain.approve"]);
+        let saved = serde_json::to_string(&first.wallets()["w1"]).unwrap();
+        drop(first);
+
+        // The restarted fleet would decide nothing, but still stakes
+        let (mut restarted, plugin, _) = chain_service(ChainPlugin::default());
+        restarted
+            .wallets
+            .insert("w1".into(), serde_json::from_str(&saved).unwrap());
+        restarted.clock = Arc::clone(&clock) as _;
+        run_chain(&mut restarted, &clock).await;
+        assert_eq!(plugin.executed(), ["chain.stake", "chain.boost"]);
+    }
+
+    /// Plugin marking every wallet on every turn, recording when it ran.
+    #[derive(Debug)]
+    struct StampPlugin {
+        clock: Arc<VirtualClock>,
+        stamps: std::sync::Mutex<Vec<(String, DateTime<Utc>)>>,
+    }
+
+    #[async_trait::async_trait]
+    impl ActionPlugin for StampPlugin {
+        fn id(&self) -> &'static str {
+            "stamp"
+        }
+
+        fn name(&self) -> &'static str {
+            "Stamp"
+        }
+
+        fn available_actions(&self) -> Vec<ActionId> {
+            vec![ActionId::new("stamp.mark")]
+        }
+
+        async fn decide_action(
+            &self,
+            _wallet: &WalletState,
+            _profile: &BehaviorProfile,
+            _context: &mut fleet_core::plugins::PluginContext<'_>,
+        ) -> fleet_core::Result<Option<Action>> {
+            Ok(Some(Action::new("stamp.mark", "Mark")))
+        }
+
+        async fn execute_action(
+            &self,
+            _action: &Action,
+            wallet: &WalletState,
+            _nonce: u64,
+        ) -> fleet_core::Result<ActionResult> {
+            self.stamps.lock().unwrap().push((wallet.id.clone(), self.clock.now()));
+            Ok(ActionResult::success(alloy::primitives::TxHash::ZERO))
+        }
+
+        async fn read_state(&self, _address: Address) -> fleet_core::Result<serde_json::Value> {
+            Ok(serde_json::Value::Null)
+        }
+    }
+
+    /// A fleet of `count` wallets acting every four hours, on `clock`.
+    fn stamp_service(count: u16, clock: &Arc<VirtualClock>) -> (FleetService, Arc<StampPlugin>) {
+        stamp_service_with(count, clock, |_| {})
+    }
+
+    /// A [`stamp_service`] with its settings adjusted by `configure`.
+    fn stamp_service_with(
+        count: u16,
+        clock: &Arc<VirtualClock>,
+        configure: impl FnOnce(&mut Settings),
+    ) -> (FleetService, Arc<StampPlugin>) {
+        let mut settings = test_settings();
+        settings.plugins.enabled = vec!["stamp".into()];
+        settings.profiles.insert(
+            "steady".into(),
+            ProfileConfig {
+                action_interval_secs: Some(14_400),
+                active_hours_start: Some(0),
+                active_hours_end: Some(23),
+                afk_probability: Some(0.0),
+                burst_probability: Some(0.0),
+                ..ProfileConfig::default()
+            },
+        );
+        settings.cold_start.max_actions_per_minute = 8;
+        settings.wallets = (1..=count)
+            .map(|i| WalletConfig {
+                id: format!("w{i:03}"),
+                address: Address::left_padding_from(&i.to_be_bytes()),
+                profile: "steady".into(),
+                key_source: None,
+                private_key: None,
+                enabled: true,
+                group: None,
+                retiring: false,
+                sweep_to: None,
+                standby: false,
+                budget: BudgetConfig::default(),
+            })
+            .collect();
+        configure(&mut settings);
+
+        let plugin = Arc::new(StampPlugin {
+            clock: Arc::clone(clock),
+            stamps: std::sync::Mutex::default(),
+        });
+        let mut registry = PluginRegistry::new();
+        registry.register(Arc::clone(&plugin) as Arc<dyn ActionPlugin>).unwrap();
+        let signers = Keyring::development(settings.wallets.iter().map(|w| w.id.as_str())).unwrap();
+        let runtime = Runtime {
+            pool: Arc::new(ProviderPool::single(
+                "primary",
+                Arc::new(MockProvider::with_chain_id(31337)),
+            )),
+            registry,
+            clock: Arc::clone(clock) as _,
+            seed: Some(7),
+            signers,
+        };
+        (FleetService::with_runtime(settings, false, runtime), plugin)
+    }
+
+    #[tokio::test]
+    async fn overdue_wallets_ramp_up_after_restart() {
+        use chrono::TimeZone;
+
+        let clock = Arc::new(VirtualClock::new(
+            Utc.with_ymd_and_hms(2025, 1, 1, 12, 0, 0).unwrap(),
+        ));
+        let (first, _) = stamp_service(200, &clock);
+        let saved = serde_json::to_string(first.wallets()).unwrap();
+        drop(first);
+
+        // Down for two hours, every wallet's next action passed
+        clock.advance(chrono::Duration::hours(2));
+        let restart = clock.now();
+        let (mut restarted, plugin) = stamp_service(200, &clock);
+        restarted.wallets = serde_json::from_str(&saved).unwrap();
+
+        restarted.process_tick().await;
+        let progress = restarted.snapshot().cold_start.unwrap();
+        assert_eq!(progress.wallets, 200);
+        assert!(progress.ends_at < restart + chrono::Duration::minutes(30));
+
+        // Wallets whose varied interval is short may be due again soon after
+        // the window, so the clock stops at its end
+        let end = restart + chrono::Duration::minutes(40);
+        while let Some(next) = restarted.next_wakeup().filter(|next| *next < end) {
+            clock.set(next.max(clock.now() + chrono::Duration::milliseconds(1)));
+            restarted.process_tick().await;
+        }
+        clock.set(end);
+        restarted.process_tick().await;
+
+        let stamps = plugin.stamps.lock().unwrap().clone();
+        assert_eq!(stamps.len(), 200, "every wallet acts once");
+        let mut per_minute: HashMap<i64, usize> = HashMap::new();
+        for (_, at) in &stamps {
+            // First-cycle reads may hold the last wallets back a moment
+            assert!(*at - restart < chrono::Duration::minutes(31));
+            *per_minute.entry((*at - restart).num_minutes()).or_default() += 1;
+        }
+        assert!(per_minute.len() >= 25, "spread over the window: {per_minute:?}");
+        assert!(per_minute.values().all(|count| *count <= 8), "{per_minute:?}");
+        assert!(restarted.snapshot().cold_start.is_none(), "the ramp is over");
+    }
+
+    #[tokio::test]
+    async fn breakers_and_afk_survive_restart() {
+        use chrono::TimeZone;
+
+        let clock = Arc::new(VirtualClock::new(
+            Utc.with_ymd_and_hms(2025, 1, 1, 12, 0, 0).unwrap(),
+        ));
+        let (mut first, _) = stamp_service(3, &clock);
+        let tripped_at = clock.now();
+        for _ in 0..first.settings.safety.max_consecutive_errors {
+            first.circuit_breaker.record_error("w001");
+        }
+        let afk_until = clock.now() + chrono::Duration::hours(3);
+        let afk = first.wallets.get_mut("w002").unwrap();
+        afk.set_afk(afk_until);
+        afk.schedule_next(afk_until);
+        let saved = serde_json::to_string(&first.state()).unwrap();
+        drop(first);
+
+        // Down for 20 minutes, within the cooldown and the AFK period
+        clock.advance(chrono::Duration::minutes(20));
+        let restart = clock.now();
+        let (mut restarted, plugin) = stamp_service(3, &clock);
+        restarted.restore_state(FleetState::from_json(&saved).unwrap());
+
+        // The cooldown continues from the original trip
+        assert!(restarted.circuit_breaker.is_tripped("w001"));
+        let remaining = restarted.circuit_breaker.time_until_reset("w001").unwrap();
+        assert!(remaining.abs_diff(Duration::from_secs(40 * 60)) <= Duration::from_secs(1));
+        assert_eq!(restarted.wallets["w002"].afk_until, Some(afk_until));
+
+        // Only the wallet neither tripped nor AFK is ramped up
+        restarted.process_tick().await;
+        assert_eq!(restarted.snapshot().cold_start.unwrap().wallets, 1);
+
+        let end = restart + chrono::Duration::hours(4);
+        while clock.now() < end {
+            let next = restarted.next_wakeup().unwrap();
+            clock.set(next.max(clock.now() + chrono::Duration::milliseconds(1)));
+            restarted.process_tick().await;
+        }
+        let stamps = plugin.stamps.lock().unwrap().clone();
+        let first_of = |id: &str| stamps.iter().find(|(w, _)| w == id).map(|(_, at)| *at);
+        assert!(first_of("w001").unwrap() > tripped_at + chrono::Duration::hours(1));
+        assert!(first_of("w002").unwrap() >= afk_until);
+        assert!(first_of("w003").unwrap() < restart + chrono::Duration::minutes(30));
+    }
+
+    #[tokio::test]
+    async fn legacy_state_files_restore_wallets() {
+        let clock = Arc::new(VirtualClock::new(Utc::now()));
+        let (first, _) = stamp_service(2, &clock);
+        let saved = serde_json::to_string(first.wallets()).unwrap();
+        drop(first);
+
+        let (mut restarted, _) = stamp_service(2, &clock);
+        let state = FleetState::from_json(&saved).unwrap();
+        assert_eq!(state.version, 1);
+        restarted.restore_state(state);
+        assert_eq!(restarted.wallets().len(), 2);
+        assert_eq!(restarted.circuit_breaker.tripped_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn pnl_ledger_survives_restarts() {
+        use alloy::primitives::TxHash;
+        use chrono::TimeZone;
+        use fleet_core::plugins::DataFlow;
+
+        let dir = tempfile::tempdir().unwrap();
+        let pnl_file = dir.path().join("pnl.jsonl");
+        let clock = Arc::new(VirtualClock::new(
+            Utc.with_ymd_and_hms(2025, 1, 1, 12, 0, 0).unwrap(),
+        ));
+        let configure = |settings: &mut Settings| {
+            settings.service.pnl_file = Some(pnl_file.clone());
+        };
+        let action = Action::new("stamp.mark", "Mark");
+        let staked = ActionResult::success_with_gas(TxHash::repeat_byte(1), 100)
+            .with_effective_gas_price(2)
+            .with_data_flows(vec![DataFlow::Staked {
+                amount: U256::from(500),
+                opened: true,
+            }]);
+
+        let (mut first, _) = stamp_service_with(1, &clock, configure);
+        first.load_state().unwrap();
+        first.record_pnl("w001", &action, &staked);
+        first.record_pnl("w001", &action, &staked);
+        // Never mined, so nothing was paid
+        let dropped = ActionResult::dropped(TxHash::repeat_byte(2), "gone");
+        first.record_pnl("w001", &action, &dropped);
+        let pnl = first.snapshot().pnl;
+        assert_eq!(pnl.lifetime.data_out, U256::from(500));
+        assert_eq!(pnl.lifetime.gas_wei, U256::from(200));
+        assert_eq!(pnl.by_wallet["w001"], pnl.lifetime);
+        drop(first);
+
+        let (mut restarted, _) = stamp_service_with(1, &clock, configure);
+        restarted.load_state().unwrap();
+        assert_eq!(restarted.snapshot().pnl, pnl);
+        restarted.record_pnl("w001", &action, &staked);
+        assert_eq!(restarted.snapshot().pnl, pnl);
+        // What was spent before the restart is not the day's activity again
+        let report = restarted.reports.so_far(&restarted.report_snapshot());
+        assert_eq!(report.totals.activity.data_out, U256::ZERO);
+        let status = restarted.status().to_string();
+        assert!(
+            status.contains("DATA: 0 wei in, 500 wei out, net -500 wei"),
+            "{status}"
+        );
+    }
+
+    #[tokio::test]
+    async fn journal_holds_hashes_not_keys() {
+        use chrono::TimeZone;
+        use fleet_core::sensitive::redact;
+
+        let dir = tempfile::tempdir().unwrap();
+        let journal = dir.path().join("plans.jsonl");
+        let clock = Arc::new(VirtualClock::new(
+            Utc.with_ymd_and_hms(2025, 1, 1, 12, 0, 0).unwrap(),
+        ));
+        let (mut service, plugin) = stamp_service_with(2, &clock, |settings| {
+            settings.review.mode = crate::config::ReviewMode::Delay;
+            settings.review.delay_secs = 60;
+            settings.review.journal = Some(journal.clone());
+        });
+        for _ in 0..3 {
+            service.process_tick().await;
+            clock.advance(chrono::Duration::hours(4));
+        }
+        assert!(!plugin.stamps.lock().unwrap().is_empty());
+
+        let lines = std::fs::read_to_string(&journal).unwrap();
+        let entries: Vec<serde_json::Value> = lines
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        let planned = entries.iter().find(|e| e["event"] == "planned").unwrap();
+        assert!(planned["batch"]["actions"][0]["data_hash"].is_string());
+        let executed = entries.iter().find(|e| e["event"] == "executed").unwrap();
+        assert_eq!(
+            executed["tx_hash"],
+            serde_json::json!(alloy::primitives::TxHash::ZERO)
+        );
+
+        for index in 0..2 {
+            let key = crate::signer::development_key(index).unwrap();
+            assert!(!lines.contains(&key), "{lines}");
+        }
+        assert_eq!(redact(&lines), lines);
+    }
+
+    /// A [`stamp_service`] of two wallets run as `node`, sharing its lease
+    /// and state file in `dir` with other nodes.
+    fn leader_service(
+        node: &str,
+        dir: &std::path::Path,
+        clock: &Arc<VirtualClock>,
+    ) -> (FleetService, Arc<StampPlugin>) {
+        stamp_service_with(2, clock, |settings| {
+            settings.service.state_file = Some(dir.join("state.json"));
+            settings.review.journal = Some(dir.join(format!("{node}.jsonl")));
+            settings.leader = crate::config::LeaderConfig {
+                enabled: true,
+                node_id: Some(node.into()),
+                lease_file: Some(dir.join("fleet.lease")),
+                ..crate::config::LeaderConfig::default()
+            };
+        })
+    }
+
+    #[tokio::test]
+    async fn lease_lost_after_deciding_sends_nothing() {
+        use chrono::TimeZone;
+        use crate::leader::LeaseStore;
+
+        let dir = tempfile::tempdir().unwrap();
+        let clock = Arc::new(VirtualClock::new(
+            Utc.with_ymd_and_hms(2025, 1, 1, 12, 0, 0).unwrap(),
+        ));
+        let (mut service, plugin) = leader_service("a", dir.path(), &clock);
+        service.heartbeat().await;
+        assert!(service.is_leader());
+
+        // Another node took the lease over without this one noticing
+        let later = clock.now() + chrono::Duration::seconds(31);
+        FileLease::new(dir.path().join("fleet.lease"))
+            .acquire("b", later, later + chrono::Duration::seconds(30))
+            .unwrap();
+
+        service.process_tick().await;
+        assert!(plugin.stamps.lock().unwrap().is_empty(), "nothing sent");
+        assert!(!service.is_leader());
+        let snapshot = service.snapshot();
+        assert_eq!(snapshot.failed_actions, 0);
+        assert_eq!(snapshot.tripped_wallets, 0);
+        assert_eq!(service.circuit_breaker.error_count("w001"), 0);
+    }
+
+    #[tokio::test]
+    async fn standby_resumes_schedules_after_failover() {
+        use chrono::TimeZone;
+
+        let dir = tempfile::tempdir().unwrap();
+        let clock = Arc::new(VirtualClock::new(
+            Utc.with_ymd_and_hms(2025, 1, 1, 12, 0, 0).unwrap(),
+        ));
+        let (mut leader, _) = leader_service("a", dir.path(), &clock);
+        let (mut standby, plugin) = leader_service("b", dir.path(), &clock);
+        leader.heartbeat().await;
+        standby.heartbeat().await;
+        assert!(leader.is_leader());
+        assert!(!standby.is_leader());
+
+        leader.process_tick().await;
+        standby.process_tick().await;
+        assert!(plugin.stamps.lock().unwrap().is_empty(), "the standby does not act");
+        leader.save_state();
+        let schedule: HashMap<_, _> = leader
+            .wallets()
+            .iter()
+            .map(|(id, w)| (id.clone(), w.next_action))
+            .collect();
+
+        // The leader stops without releasing its lease
+        drop(leader);
+        clock.advance(chrono::Duration::seconds(20));
+        standby.heartbeat().await;
+        assert!(!standby.is_leader(), "the lease is still live");
+        clock.advance(chrono::Duration::seconds(11));
+        standby.heartbeat().await;
+        assert!(standby.is_leader());
+        for (id, next) in &schedule {
+            assert_eq!(standby.wallets()[id].next_action, *next, "{id}");
+        }
+
+        // Idle in virtual time, its lease lapsed: it takes a new term
+        let next = standby.next_wakeup().unwrap();
+        clock.set(next.max(clock.now()));
+        standby.heartbeat().await;
+        standby.process_tick().await;
+        assert!(!plugin.stamps.lock().unwrap().is_empty(), "the new leader acts");
+
+        let journal = std::fs::read_to_string(dir.path().join("b.jsonl")).unwrap();
+        let entry: crate::review::JournalEntry =
+            serde_json::from_str(journal.lines().next().unwrap()).unwrap();
+        assert!(
+            matches!(entry, crate::review::JournalEntry::LeaseAcquired { term: 2, .. }),
+            "{entry:?}"
+        );
+    }
+
+    /// `wallet` holding a live position at `level`.
+    fn hold_position(wallet: &mut WalletState, level: ghostnet_actions::Level) {
+        let state = ghostnet_actions::GhostnetState {
+            position: Some(ghostnet_actions::Position {
+                amount: U256::from(100),
+                level,
+                entry_timestamp: 0,
+                last_add_timestamp: 0,
+                alive: true,
+                ghost_streak: 0,
+                pending_rewards: U256::ZERO,
+                effective_death_rate_bps: 0,
+                in_lock_period: false,
+                active_boosts: Vec::new(),
+            }),
+            ..ghostnet_actions::GhostnetState::default()
+        };
+        wallet
+            .set_plugin_state(ghostnet_actions::PLUGIN_ID, &state)
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn scans_wake_untripped_wallets_earlier_only() {
+        use ghostnet_actions::Level;
+
+        let clock = Arc::new(VirtualClock::new(Utc::now()));
+        let (mut service, _) = stamp_service_with(3, &clock, |settings| {
+            settings.triggers.enabled = true;
+        });
+        let now = clock.now();
+        let later = now + chrono::Duration::hours(4);
+        for (id, next) in [("w001", later), ("w002", later), ("w003", now)] {
+            let wallet = service.wallets.get_mut(id).unwrap();
+            hold_position(wallet, Level::Mainframe);
+            wallet.schedule_next(next);
+        }
+        for _ in 0..5 {
+            service.record_wallet_error("w002");
+        }
+        assert!(service.circuit_breaker.is_tripped("w002"));
+
+        service.handle_protocol_event(&ProtocolEvent::ScanExecuted {
+            level: Level::Mainframe,
+        });
+
+        // Only the untripped wallet due later is woken, within the window
+        let woken = service.wallets()["w001"].next_action;
+        assert!(woken >= now + chrono::Duration::minutes(1));
+        assert!(woken <= now + chrono::Duration::minutes(10));
+        assert_eq!(service.wallets()["w002"].next_action, later);
+        assert_eq!(service.wallets()["w003"].next_action, now);
+        assert_eq!(service.woken, HashSet::from(["w001".to_string()]));
+    }
 }
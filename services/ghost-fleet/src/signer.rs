@@ -0,0 +1,648 @@
+//! Wallet signing keys.
+//!
+//! Each wallet's key comes from its [`KeySource`]:
+//!
+//! - **Raw**: hex private key in the config file, for local development
+//! - **Keystore**: encrypted JSON keystore file, decrypted with a password from
+//!   an environment variable or a prompt
+//! - **Mnemonic**: derived from a seed phrase of `[mnemonics.<name>]` at
+//!   `<derivation_path>/<index>`
+//! - **Remote**: held by a remote signer (web3signer `eth1` API), so the key
+//!   never enters the process
+//!
+//! [`load_signers`] builds every wallet's [`TransactionSigner`] once at
+//! startup: a [`LocalSigner`] for the first three sources, a
+//! [`RemoteHttpSigner`] for the last. A key that cannot be loaded, or that
+//! belongs to another address than the wallet's configured `address`, fails
+//! startup with an error naming the wallet; a remote signature for another
+//! address fails the transaction. Passwords, seed phrases and decrypted key
+//! bytes are only held in buffers that are wiped on drop.
+
+use std::collections::HashMap;
+use std::env::{self, VarError};
+use std::fmt;
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+use alloy::hex;
+use alloy::primitives::Address;
+use alloy::signers::k256::ecdsa::SigningKey;
+use alloy::signers::local::coins_bip39::English;
+use alloy::signers::local::{MnemonicBuilder, PrivateKeySigner};
+use evm_provider::{LocalSigner, RemoteHttpSigner, TransactionSigner};
+use tracing::{debug, info};
+use zeroize::{ZeroizeOnDrop, Zeroizing};
+
+use crate::config::{KeySource, MnemonicConfig, Settings, WalletConfig};
+use crate::error::SignerError;
+
+/// Seed phrase of the well-known development accounts (Anvil, Hardhat).
+///
+/// Never fund these; everyone knows their keys.
+pub const DEVELOPMENT_MNEMONIC: &str =
+    "test test test test test test test test test test test junk";
+
+/// Derivation path of the development accounts.
+const DEVELOPMENT_DERIVATION_PATH: &str = "m/44'/60'/0'/0";
+
+// The secret scalar of a signing key is wiped when the key is dropped
+const _: () = {
+    const fn assert_zeroize_on_drop<T: ZeroizeOnDrop>() {}
+    assert_zeroize_on_drop::<SigningKey>();
+};
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// WALLET SIGNER
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Signer of one wallet.
+///
+/// A key held in process is wiped from memory on drop. `Debug` only shows
+/// the kind of signer and the address.
+pub struct WalletSigner {
+    /// The signer: a key held here, or a remote signer.
+    signer: Box<dyn TransactionSigner>,
+}
+
+impl WalletSigner {
+    /// Wrap a signer.
+    #[must_use]
+    pub fn new(signer: impl TransactionSigner + 'static) -> Self {
+        Self {
+            signer: Box::new(signer),
+        }
+    }
+
+    /// Sign in process with a private key.
+    #[must_use]
+    pub fn local(signer: PrivateKeySigner) -> Self {
+        Self::new(LocalSigner::new(signer))
+    }
+
+    /// Address signed for.
+    #[must_use]
+    pub fn address(&self) -> Address {
+        self.signer.address()
+    }
+
+    /// The underlying signer, to sign transactions with.
+    #[must_use]
+    pub fn signer(&self) -> &dyn TransactionSigner {
+        &*self.signer
+    }
+}
+
+impl fmt::Debug for WalletSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WalletSigner")
+            .field("signer", &self.signer)
+            .finish()
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// KEYRING
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Signing keys of the fleet, by wallet ID.
+#[derive(Debug, Default)]
+pub struct Keyring {
+    /// Signer per wallet ID.
+    signers: HashMap<String, WalletSigner>,
+}
+
+impl Keyring {
+    /// Create an empty keyring.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keyring with the development accounts, in order, for the given wallets.
+    ///
+    /// For simulations and tests; see [`DEVELOPMENT_MNEMONIC`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there are more wallets than derivation indices.
+    pub fn development<'a>(
+        wallet_ids: impl IntoIterator<Item = &'a str>,
+    ) -> Result<Self, SignerError> {
+        let mut keyring = Self::new();
+        for (index, wallet_id) in wallet_ids.into_iter().enumerate() {
+            let derivation_error = |reason: String| SignerError::Derivation {
+                wallet: wallet_id.to_string(),
+                mnemonic: "development".into(),
+                reason,
+            };
+            let index = u32::try_from(index).map_err(|e| derivation_error(e.to_string()))?;
+            let signer = derive(DEVELOPMENT_MNEMONIC, DEVELOPMENT_DERIVATION_PATH, index)
+                .map_err(derivation_error)?;
+            keyring.insert(wallet_id, WalletSigner::local(signer));
+        }
+        Ok(keyring)
+    }
+
+    /// Add or replace the signer of a wallet.
+    pub fn insert(&mut self, wallet_id: impl Into<String>, signer: WalletSigner) {
+        self.signers.insert(wallet_id.into(), signer);
+    }
+
+    /// Signer of a wallet.
+    #[must_use]
+    pub fn get(&self, wallet_id: &str) -> Option<&WalletSigner> {
+        self.signers.get(wallet_id)
+    }
+
+    /// Number of wallets with a signer.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.signers.len()
+    }
+
+    /// Whether no wallet has a signer.
+    #[allow(dead_code)] // Used in tests
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.signers.is_empty()
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// SECRETS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Source of passwords and seed phrases.
+pub trait SecretReader {
+    /// Read a secret from the environment variable `env` if given, otherwise
+    /// ask for it with `prompt`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the variable is not set or the prompt fails.
+    fn read(&self, env: Option<&str>, prompt: &str) -> io::Result<Zeroizing<String>>;
+}
+
+/// Reads secrets from environment variables, or prompts on the terminal
+/// without echoing.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EnvOrPrompt;
+
+impl SecretReader for EnvOrPrompt {
+    fn read(&self, env: Option<&str>, prompt: &str) -> io::Result<Zeroizing<String>> {
+        let Some(var) = env else {
+            return rpassword::prompt_password(prompt).map(Zeroizing::new);
+        };
+
+        match env::var(var) {
+            Ok(value) => Ok(Zeroizing::new(value)),
+            Err(VarError::NotPresent) => Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("environment variable {var} is not set"),
+            )),
+            Err(VarError::NotUnicode(_)) => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("environment variable {var} is not valid UTF-8"),
+            )),
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// LOADING
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Load the signers of all enabled wallets.
+///
+/// Wallets without a key source are skipped. Each mnemonic's phrase is read
+/// once, however many wallets derive from it.
+///
+/// # Errors
+///
+/// Returns the first signer that fails to load or does not match its
+/// wallet's configured address.
+pub fn load_signers(
+    settings: &Settings,
+    secrets: &dyn SecretReader,
+) -> Result<Keyring, SignerError> {
+    let mut keyring = Keyring::new();
+    let mut factory = SignerFactory::new(settings, secrets);
+
+    for wallet in settings.wallets.iter().filter(|w| w.enabled) {
+        let Some(signer) = factory.build(wallet)? else {
+            debug!(wallet = %wallet.id, "No key source, skipping");
+            continue;
+        };
+
+        if signer.address() != wallet.address {
+            return Err(SignerError::AddressMismatch {
+                wallet: wallet.id.clone(),
+                expected: wallet.address,
+                actual: signer.address(),
+            });
+        }
+
+        debug!(wallet = %wallet.id, signer = ?signer, "Signer loaded");
+        keyring.insert(wallet.id.clone(), signer);
+    }
+
+    info!(signers = keyring.len(), "Wallet keys loaded");
+    Ok(keyring)
+}
+
+/// Builds wallet signers from their [`KeySource`].
+struct SignerFactory<'a> {
+    /// Settings holding the mnemonics.
+    settings: &'a Settings,
+
+    /// Source of passwords and seed phrases.
+    secrets: &'a dyn SecretReader,
+
+    /// Seed phrases read so far, by mnemonic name.
+    phrases: HashMap<&'a str, Zeroizing<String>>,
+}
+
+impl<'a> SignerFactory<'a> {
+    fn new(settings: &'a Settings, secrets: &'a dyn SecretReader) -> Self {
+        Self {
+            settings,
+            secrets,
+            phrases: HashMap::new(),
+        }
+    }
+
+    /// Signer of `wallet`, or `None` if it has no key source.
+    fn build(&mut self, wallet: &WalletConfig) -> Result<Option<WalletSigner>, SignerError> {
+        let key = match (&wallet.key_source, &wallet.private_key) {
+            (Some(KeySource::Raw { private_key }), _) | (None, Some(private_key)) => {
+                from_hex(wallet, private_key.expose())?
+            }
+            (Some(KeySource::Keystore { path, password_env }), _) => {
+                let prompt = format!("Password for wallet {}: ", wallet.id);
+                let password = self
+                    .secrets
+                    .read(password_env.as_deref(), &prompt)
+                    .map_err(|source| SignerError::Secret {
+                        wallet: wallet.id.clone(),
+                        secret: format!("password of keystore {}", path.display()),
+                        source,
+                    })?;
+                from_keystore(wallet, path, &password)?
+            }
+            (Some(KeySource::Mnemonic { mnemonic, index }), _) => {
+                let derivation_error = |reason: String| SignerError::Derivation {
+                    wallet: wallet.id.clone(),
+                    mnemonic: mnemonic.clone(),
+                    reason,
+                };
+                let settings = self.settings;
+                let (name, config) = settings
+                    .mnemonics
+                    .get_key_value(mnemonic)
+                    .ok_or_else(|| derivation_error("not found in [mnemonics]".into()))?;
+
+                if !self.phrases.contains_key(name.as_str()) {
+                    let phrase = read_phrase(wallet, name, config, self.secrets)?;
+                    self.phrases.insert(name, phrase);
+                }
+                let phrase = self.phrases.get(name.as_str()).map_or("", |p| p.as_str());
+
+                derive(phrase, &config.derivation_path, *index).map_err(derivation_error)?
+            }
+            (Some(KeySource::Remote { url, timeout_secs }), _) => {
+                let timeout = Duration::from_secs(*timeout_secs);
+                let signer = RemoteHttpSigner::new(url, wallet.address, timeout).map_err(|e| {
+                    SignerError::Remote {
+                        wallet: wallet.id.clone(),
+                        reason: e.to_string(),
+                    }
+                })?;
+                return Ok(Some(WalletSigner::new(signer)));
+            }
+            (None, None) => return Ok(None),
+        };
+        Ok(Some(WalletSigner::local(key)))
+    }
+}
+
+/// Parse a hex private key.
+fn from_hex(wallet: &WalletConfig, private_key: &str) -> Result<PrivateKeySigner, SignerError> {
+    let invalid = |reason: String| SignerError::InvalidKey {
+        wallet: wallet.id.clone(),
+        reason,
+    };
+
+    let bytes =
+        Zeroizing::new(hex::decode(private_key.trim()).map_err(|e| invalid(e.to_string()))?);
+    PrivateKeySigner::from_slice(&bytes).map_err(|e| invalid(e.to_string()))
+}
+
+/// Decrypt a keystore file.
+fn from_keystore(
+    wallet: &WalletConfig,
+    path: &Path,
+    password: &str,
+) -> Result<PrivateKeySigner, SignerError> {
+    let keystore_error = |reason: String| SignerError::Keystore {
+        wallet: wallet.id.clone(),
+        path: path.to_path_buf(),
+        reason,
+    };
+
+    let secret = eth_keystore::decrypt_key(path, password).map_err(|e| match e {
+        eth_keystore::KeystoreError::MacMismatch => keystore_error("wrong password".into()),
+        other => keystore_error(other.to_string()),
+    })?;
+    let secret = Zeroizing::new(secret);
+
+    PrivateKeySigner::from_slice(&secret).map_err(|e| keystore_error(e.to_string()))
+}
+
+/// Read the phrase of a mnemonic.
+fn read_phrase(
+    wallet: &WalletConfig,
+    name: &str,
+    config: &MnemonicConfig,
+    secrets: &dyn SecretReader,
+) -> Result<Zeroizing<String>, SignerError> {
+    let prompt = format!("Seed phrase of mnemonic {name}: ");
+    secrets
+        .read(config.phrase_env.as_deref(), &prompt)
+        .map_err(|source| SignerError::Secret {
+            wallet: wallet.id.clone(),
+            secret: format!("phrase of mnemonic '{name}'"),
+            source,
+        })
+}
+
+/// Hex private key of the development account at `index`, for tests that
+/// check it never shows up.
+#[cfg(test)]
+pub fn development_key(index: u32) -> Result<String, String> {
+    let signer = derive(DEVELOPMENT_MNEMONIC, DEVELOPMENT_DERIVATION_PATH, index)?;
+    Ok(hex::encode(signer.to_bytes()))
+}
+
+/// Derive the key at `<derivation_path>/<index>` from a phrase.
+fn derive(phrase: &str, derivation_path: &str, index: u32) -> Result<PrivateKeySigner, String> {
+    let path = format!("{}/{index}", derivation_path.trim_end_matches('/'));
+
+    MnemonicBuilder::<English>::default()
+        .phrase(phrase.trim())
+        .derivation_path(&path)
+        .and_then(|builder| builder.build())
+        .map_err(|e| format!("{path}: {e}"))
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::cell::RefCell;
+    use std::path::PathBuf;
+
+    use super::*;
+
+    /// Secrets from a fixed map, recording every request.
+    #[derive(Default)]
+    struct FixedSecrets {
+        values: HashMap<String, String>,
+        requests: RefCell<Vec<String>>,
+    }
+
+    impl FixedSecrets {
+        fn with(mut self, key: &str, value: &str) -> Self {
+            self.values.insert(key.into(), value.into());
+            self
+        }
+    }
+
+    impl SecretReader for FixedSecrets {
+        fn read(&self, env: Option<&str>, prompt: &str) -> io::Result<Zeroizing<String>> {
+            let key = env.unwrap_or(prompt);
+            self.requests.borrow_mut().push(key.into());
+            self.values
+                .get(key)
+                .map(|value| Zeroizing::new(value.clone()))
+                .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, key.to_string()))
+        }
+    }
+
+    fn fixture(name: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/keystores")
+            .join(name)
+    }
+
+    fn settings(wallets: &str) -> Settings {
+        let config = format!(
+            r#"
+            [chain]
+            chain_id = 31337
+            rpc_url = "http://localhost:8545"
+
+            [mnemonics.fleet]
+            phrase_env = "FLEET_MNEMONIC"
+
+            {wallets}
+            "#
+        );
+        toml::from_str(&config).unwrap()
+    }
+
+    #[test]
+    fn raw_keys_and_legacy_private_key() {
+        let settings = settings(
+            r#"
+            [[wallets]]
+            id = "raw"
+            address = "0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf"
+            profile = "p"
+            key_source = { type = "raw", private_key = "0x0000000000000000000000000000000000000000000000000000000000000001" }
+
+            [[wallets]]
+            id = "legacy"
+            address = "0x2B5AD5c4795c026514f8317c7a215E218DcCD6cF"
+            profile = "p"
+            private_key = "0000000000000000000000000000000000000000000000000000000000000002"
+
+            [[wallets]]
+            id = "no_key"
+            address = "0x0000000000000000000000000000000000000003"
+            profile = "p"
+            "#,
+        );
+
+        let keyring = load_signers(&settings, &FixedSecrets::default()).unwrap();
+
+        assert_eq!(keyring.len(), 2);
+        assert_eq!(
+            keyring.get("raw").unwrap().address(),
+            settings.wallets[0].address
+        );
+        assert_eq!(
+            keyring.get("legacy").unwrap().address(),
+            settings.wallets[1].address
+        );
+        assert!(keyring.get("no_key").is_none());
+        // Debug output never contains the key
+        assert!(!format!("{keyring:?}").contains("0000000001"));
+        assert!(!format!("{:?}", settings.wallets[0].key_source).contains("0000000001"));
+        assert!(!format!("{:?}", settings.wallets[1]).contains("0000000002"));
+    }
+
+    #[test]
+    fn keystore_fixtures_decrypt() {
+        let wallets = format!(
+            r#"
+            [[wallets]]
+            id = "whale_1"
+            address = "0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf"
+            profile = "p"
+            key_source = {{ type = "keystore", path = "{}", password_env = "WHALE_PASSWORD" }}
+
+            [[wallets]]
+            id = "grinder_1"
+            address = "0x2B5AD5c4795c026514f8317c7a215E218DcCD6cF"
+            profile = "p"
+            key_source = {{ type = "keystore", path = "{}" }}
+            "#,
+            fixture("whale_1.json").display(),
+            fixture("grinder_1.json").display(),
+        );
+        let settings = settings(&wallets);
+        let secrets = FixedSecrets::default()
+            .with("WHALE_PASSWORD", "correct horse battery staple")
+            .with("Password for wallet grinder_1: ", "hunter2");
+
+        let keyring = load_signers(&settings, &secrets).unwrap();
+
+        assert_eq!(keyring.len(), 2);
+        assert_eq!(
+            keyring.get("whale_1").unwrap().address(),
+            settings.wallets[0].address
+        );
+        assert_eq!(
+            keyring.get("grinder_1").unwrap().address(),
+            settings.wallets[1].address
+        );
+    }
+
+    #[test]
+    fn wrong_password_names_the_wallet() {
+        let wallets = format!(
+            r#"
+            [[wallets]]
+            id = "whale_1"
+            address = "0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf"
+            profile = "p"
+            key_source = {{ type = "keystore", path = "{}", password_env = "WHALE_PASSWORD" }}
+            "#,
+            fixture("whale_1.json").display(),
+        );
+        let secrets = FixedSecrets::default().with("WHALE_PASSWORD", "wrong");
+
+        let error = load_signers(&settings(&wallets), &secrets).unwrap_err();
+
+        assert!(matches!(&error, SignerError::Keystore { wallet, .. } if wallet == "whale_1"));
+        assert!(error.to_string().contains("whale_1"));
+        assert!(error.to_string().contains("wrong password"));
+
+        // A missing secret is an error naming the wallet too
+        let error = load_signers(&settings(&wallets), &FixedSecrets::default()).unwrap_err();
+        assert!(matches!(&error, SignerError::Secret { wallet, .. } if wallet == "whale_1"));
+    }
+
+    #[test]
+    fn mnemonic_derives_known_addresses() {
+        let settings = settings(
+            r#"
+            [[wallets]]
+            id = "first"
+            address = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266"
+            profile = "p"
+            key_source = { type = "mnemonic", mnemonic = "fleet", index = 0 }
+
+            [[wallets]]
+            id = "third"
+            address = "0x3C44CdDdB6a900fa2b585dd299e03d12FA4293BC"
+            profile = "p"
+            key_source = { type = "mnemonic", mnemonic = "fleet", index = 2 }
+            "#,
+        );
+        let secrets = FixedSecrets::default().with("FLEET_MNEMONIC", DEVELOPMENT_MNEMONIC);
+
+        let keyring = load_signers(&settings, &secrets).unwrap();
+
+        assert_eq!(
+            keyring.get("first").unwrap().address(),
+            settings.wallets[0].address
+        );
+        assert_eq!(
+            keyring.get("third").unwrap().address(),
+            settings.wallets[1].address
+        );
+        // The phrase is read once for both wallets
+        assert_eq!(secrets.requests.borrow().len(), 1);
+
+        // The development keyring uses the same accounts
+        let development = Keyring::development(["first", "second", "third"]).unwrap();
+        assert_eq!(
+            development.get("third").unwrap().address(),
+            settings.wallets[1].address
+        );
+    }
+
+    #[test]
+    fn address_mismatch_is_rejected() {
+        let settings = settings(
+            r#"
+            [[wallets]]
+            id = "funded"
+            address = "0x70997970C51812dc3A010C7d01b50e0d17dc79C8"
+            profile = "p"
+            key_source = { type = "mnemonic", mnemonic = "fleet", index = 0 }
+            "#,
+        );
+        let secrets = FixedSecrets::default().with("FLEET_MNEMONIC", DEVELOPMENT_MNEMONIC);
+
+        let error = load_signers(&settings, &secrets).unwrap_err();
+
+        assert!(matches!(error, SignerError::AddressMismatch { wallet, .. } if wallet == "funded"));
+    }
+
+    #[test]
+    fn remote_signers_sign_for_the_wallet() {
+        let settings = settings(
+            r#"
+            [[wallets]]
+            id = "kms"
+            address = "0x70997970C51812dc3A010C7d01b50e0d17dc79C8"
+            profile = "p"
+            key_source = { type = "remote", url = "http://127.0.0.1:9000", timeout_secs = 5 }
+
+            [[wallets]]
+            id = "broken"
+            address = "0x3C44CdDdB6a900fa2b585dd299e03d12FA4293BC"
+            profile = "p"
+            enabled = false
+            key_source = { type = "remote", url = "not a url" }
+            "#,
+        );
+
+        // The signer is not asked until there is something to sign
+        let keyring = load_signers(&settings, &FixedSecrets::default()).unwrap();
+        let kms = keyring.get("kms").unwrap();
+        assert_eq!(kms.address(), settings.wallets[0].address);
+        assert!(format!("{kms:?}").contains("RemoteHttpSigner"));
+
+        let mut settings = settings;
+        settings.wallets[1].enabled = true;
+        let error = load_signers(&settings, &FixedSecrets::default()).unwrap_err();
+        assert!(matches!(error, SignerError::Remote { wallet, .. } if wallet == "broken"));
+    }
+}
@@ -0,0 +1,605 @@
+//! Deterministic fleet simulation on a virtual clock.
+//!
+//! Behaviour changes are hard to evaluate against a live chain: schedules
+//! span hours and results depend on chance. The [`SimulationEngine`] runs the
+//! unchanged [`FleetService`] against a [`MockProvider`] whose transactions
+//! revert, get dropped and use gas according to the configured
+//! [`SimulationConfig`](crate::config::SimulationConfig), while a
+//! [`VirtualClock`] jumps straight to the next moment a wallet is due. Every
+//! random number generator is seeded, so a month of fleet activity takes
+//! seconds and the same seed always produces the same [`SimulationReport`].
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use alloy::primitives::{Address, Bytes, U256};
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use evm_provider::{ChainProvider, TransactionReceipt};
+use evm_provider::mock::{MockProvider, TxOutcomes};
+use evm_provider::pool::ProviderPool;
+use fleet_core::clock::{Clock, SharedClock, VirtualClock};
+use fleet_core::plugins::{
+    Action, ActionId, ActionPlugin, ActionResult, DataFlow, PendingTx, PluginContext,
+    PluginRegistry,
+};
+use fleet_core::profiles::BehaviorProfile;
+use fleet_core::wallet::WalletState;
+use tracing::info;
+
+use crate::config::{ChainConfig, ReviewConfig, Settings};
+use crate::service::{FleetService, Runtime};
+use crate::signer::Keyring;
+
+/// Smallest step the virtual clock takes, so wallets that are due but held
+/// back (e.g. by the rate limiter) don't stall the simulation.
+const MIN_STEP: chrono::Duration = chrono::Duration::seconds(1);
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// SIMULATION REPORT
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Outcome of a simulated run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimulationReport {
+    /// Seed the run used.
+    pub seed: u64,
+
+    /// Virtual start time.
+    pub start: DateTime<Utc>,
+
+    /// Virtual end time.
+    pub end: DateTime<Utc>,
+
+    /// Service ticks processed.
+    pub ticks: u64,
+
+    /// Actions attempted per wallet, including wallets that never acted.
+    pub actions_per_wallet: BTreeMap<String, u64>,
+
+    /// Actions attempted per action ID.
+    pub actions_by_type: BTreeMap<String, u64>,
+
+    /// Actions per outcome (`succeeded`, `reverted`, ...).
+    pub actions_by_status: BTreeMap<String, u64>,
+
+    /// Actions whose transaction had to be replaced, per outcome
+    /// (`cancelled`, `abandoned`, ...).
+    pub replacements_by_outcome: BTreeMap<String, u64>,
+
+    /// Circuit breaker trips across all wallets.
+    pub breaker_trips: u64,
+
+    /// Estimated gas spent in wei.
+    pub gas_spent_wei: u128,
+}
+
+impl fmt::Display for SimulationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Simulation (seed {}): {} to {} in {} ticks",
+            self.seed, self.start, self.end, self.ticks
+        )?;
+
+        let sections = [
+            ("Actions per wallet", &self.actions_per_wallet),
+            ("Actions by type", &self.actions_by_type),
+            ("Actions by outcome", &self.actions_by_status),
+            ("Replaced transactions", &self.replacements_by_outcome),
+        ];
+        for (title, counts) in sections {
+            writeln!(f, "{title}:")?;
+            for (key, count) in counts {
+                writeln!(f, "  {key:<32} {count:>8}")?;
+            }
+        }
+
+        writeln!(f, "Circuit breaker trips: {}", self.breaker_trips)?;
+        write!(f, "Estimated gas spent: {} wei", self.gas_spent_wei)
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// SIMULATION ENGINE
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Drives a [`FleetService`] through virtual time.
+///
+/// # Example
+///
+/// ```ignore
+/// let settings = Settings::load("config.toml")?;
+/// let report = SimulationEngine::new(settings)?.run().await;
+/// println!("{report}");
+/// ```
+#[derive(Debug)]
+pub struct SimulationEngine {
+    /// Service under simulation.
+    service: FleetService,
+
+    /// Virtual clock shared with the service.
+    clock: Arc<VirtualClock>,
+
+    /// Seed of the run.
+    seed: u64,
+
+    /// Virtual start time.
+    start: DateTime<Utc>,
+
+    /// Virtual end time.
+    end: DateTime<Utc>,
+}
+
+impl SimulationEngine {
+    /// Set up a simulation of the configured fleet.
+    ///
+    /// Uses the `[simulation]` section of the settings; the chain settings
+    /// only contribute the chain ID, and `[review]` is ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the simulated balances are not valid amounts.
+    pub fn new(mut settings: Settings) -> Result<Self> {
+        let config = settings.simulation.clone();
+        let start = config.start;
+        let end = start + chrono::Duration::days(i64::from(config.days));
+        let clock = Arc::new(VirtualClock::new(start));
+
+        let native_balance: U256 = config
+            .native_balance
+            .parse()
+            .context("Invalid simulation.native_balance")?;
+        let token_balance: U256 = config
+            .token_balance
+            .parse()
+            .context("Invalid simulation.token_balance")?;
+
+        let provider = Arc::new(MockProvider::with_chain_id(settings.chain.chain_id));
+        provider.set_gas_price(config.gas_price);
+        provider.set_tx_outcomes(TxOutcomes {
+            revert_rate: config.revert_rate,
+            drop_rate: config.drop_rate,
+            gas_used: (config.gas_used_min, config.gas_used_max),
+            latency: (
+                Duration::from_millis(config.latency_ms_min),
+                Duration::from_millis(config.latency_ms_max),
+            ),
+            seed: config.seed,
+        });
+        for wallet in settings.wallets.iter().filter(|w| w.enabled) {
+            provider.set_balance(wallet.address, native_balance);
+            if let Some(data_token) = settings.chain.ghostnet.data_token {
+                provider.set_token_balance(data_token, wallet.address, token_balance);
+            }
+        }
+
+        let receipt_timeout = Duration::from_millis(config.receipt_timeout_ms);
+        let registry = simulated_registry(
+            &FleetService::create_registry(&settings, Arc::clone(&provider))?,
+            &provider,
+            receipt_timeout,
+        )?;
+
+        // The mock chain accepts any key
+        let signers = Keyring::development(settings.wallets.iter().map(|w| w.id.as_str()))?;
+
+        let runtime = Runtime {
+            pool: Arc::new(ProviderPool::single(
+                ChainConfig::PRIMARY_ENDPOINT,
+                Arc::clone(&provider),
+            )),
+            registry,
+            clock: Arc::clone(&clock) as SharedClock,
+            seed: Some(config.seed),
+            signers,
+        };
+        // No operator reviews a simulated fleet's plans
+        settings.review = ReviewConfig::default();
+        let service = FleetService::with_runtime(settings, false, runtime);
+
+        Ok(Self {
+            service,
+            clock,
+            seed: config.seed,
+            start,
+            end,
+        })
+    }
+
+    /// Run the simulation to the end of the configured time span.
+    pub async fn run(mut self) -> SimulationReport {
+        info!(
+            seed = self.seed,
+            start = %self.start,
+            end = %self.end,
+            "Starting simulation"
+        );
+
+        let mut ticks = 0;
+        loop {
+            self.service.process_tick().await;
+            ticks += 1;
+
+            let now = self.clock.now();
+            let Some(next) = self.service.next_wakeup() else {
+                break;
+            };
+            let next = next.max(now + MIN_STEP);
+            if next >= self.end {
+                break;
+            }
+            self.clock.set(next);
+        }
+        self.clock.set(self.end);
+
+        let report = self.report(ticks);
+        info!(
+            ticks,
+            actions = report.actions_per_wallet.values().sum::<u64>(),
+            breaker_trips = report.breaker_trips,
+            "Simulation finished"
+        );
+        report
+    }
+
+    /// Build the report from the service's metrics.
+    fn report(&self, ticks: u64) -> SimulationReport {
+        let snapshot = self.service.snapshot();
+
+        let mut actions_per_wallet: BTreeMap<_, _> = self
+            .service
+            .wallets()
+            .keys()
+            .map(|id| (id.clone(), 0))
+            .collect();
+        actions_per_wallet.extend(snapshot.actions_by_wallet);
+
+        SimulationReport {
+            seed: self.seed,
+            start: self.start,
+            end: self.end,
+            ticks,
+            actions_per_wallet,
+            actions_by_type: snapshot.actions_by_type.into_iter().collect(),
+            actions_by_status: snapshot
+                .actions_by_status
+                .into_iter()
+                .map(|(status, count)| (status.as_str().to_string(), count))
+                .collect(),
+            replacements_by_outcome: snapshot
+                .replacements_by_outcome
+                .into_iter()
+                .map(|(outcome, count)| (outcome.as_str().to_string(), count))
+                .collect(),
+            breaker_trips: self.service.circuit_breaker().total_trips(),
+            gas_spent_wei: snapshot.total_gas_cost_wei,
+        }
+    }
+}
+
+/// Copy of `registry` with every plugin's execution simulated.
+fn simulated_registry(
+    registry: &PluginRegistry,
+    provider: &Arc<MockProvider>,
+    receipt_timeout: Duration,
+) -> Result<PluginRegistry> {
+    let mut simulated = PluginRegistry::new();
+    for plugin in registry.ordered_plugins() {
+        let priority = registry
+            .priority(plugin.id())
+            .unwrap_or(fleet_core::plugins::DEFAULT_PRIORITY);
+        let plugin = SimulatedPlugin {
+            inner: plugin,
+            provider: Arc::clone(provider),
+            receipt_timeout,
+        };
+        simulated.register_with_priority(Arc::new(plugin), priority)?;
+    }
+    Ok(simulated)
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// SIMULATED PLUGIN
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Plugin wrapper that decides like the wrapped plugin but executes against
+/// the simulated chain.
+///
+/// Instead of building and signing a real transaction, execution sends a
+/// placeholder to the [`MockProvider`] and reports the receipt it draws.
+#[derive(Debug)]
+struct SimulatedPlugin {
+    /// Plugin making the decisions.
+    inner: Arc<dyn ActionPlugin>,
+
+    /// Simulated chain.
+    provider: Arc<MockProvider>,
+
+    /// How long to wait for a receipt.
+    receipt_timeout: Duration,
+}
+
+#[async_trait]
+impl ActionPlugin for SimulatedPlugin {
+    fn id(&self) -> &str {
+        self.inner.id()
+    }
+
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn available_actions(&self) -> Vec<ActionId> {
+        self.inner.available_actions()
+    }
+
+    fn default_cooldown(&self, action: &ActionId) -> Option<chrono::Duration> {
+        self.inner.default_cooldown(action)
+    }
+
+    async fn decide_action(
+        &self,
+        wallet: &WalletState,
+        profile: &BehaviorProfile,
+        context: &mut PluginContext<'_>,
+    ) -> fleet_core::Result<Option<Action>> {
+        self.inner.decide_action(wallet, profile, context).await
+    }
+
+    async fn execute_action(
+        &self,
+        action: &Action,
+        wallet: &WalletState,
+        nonce: u64,
+    ) -> fleet_core::Result<ActionResult> {
+        let placeholder = Bytes::copy_from_slice(action.id.as_str().as_bytes());
+        let tx_hash = self.provider.send_raw_transaction(placeholder).await?;
+        let latency = self.provider.receipt_latency(tx_hash).unwrap_or_default();
+        let gas_price = self.provider.gas_price().await?;
+
+        let result = match self
+            .provider
+            .wait_for_receipt(tx_hash, self.receipt_timeout)
+            .await
+        {
+            Ok(receipt) => {
+                let result = ActionResult::from_receipt(&receipt)
+                    .with_effective_gas_price(gas_price)
+                    .with_duration(latency);
+                let result = match self.state_from_receipt(action, wallet, &receipt) {
+                    Some(state) if receipt.success => result.with_plugin_state(state),
+                    _ => result,
+                };
+                if receipt.success {
+                    result.with_data_flows(self.data_flows(action, wallet, &receipt))
+                } else {
+                    result
+                }
+            }
+            Err(e) => ActionResult::dropped(tx_hash, e.to_string())
+                .with_duration(latency.min(self.receipt_timeout)),
+        };
+        Ok(result.with_detail(serde_json::json!({ "simulated": true, "nonce": nonce })))
+    }
+
+    async fn build_replacement(
+        &self,
+        action: &Action,
+        original: &PendingTx,
+        bump_pct: u32,
+    ) -> fleet_core::Result<Option<Bytes>> {
+        self.inner.build_replacement(action, original, bump_pct).await
+    }
+
+    async fn read_state(&self, address: Address) -> fleet_core::Result<serde_json::Value> {
+        self.inner.read_state(address).await
+    }
+
+    fn state_schema_version(&self) -> u32 {
+        self.inner.state_schema_version()
+    }
+
+    fn state_from_receipt(
+        &self,
+        action: &Action,
+        wallet: &WalletState,
+        receipt: &TransactionReceipt,
+    ) -> Option<serde_json::Value> {
+        self.inner.state_from_receipt(action, wallet, receipt)
+    }
+
+    fn data_flows(
+        &self,
+        action: &Action,
+        wallet: &WalletState,
+        receipt: &TransactionReceipt,
+    ) -> Vec<DataFlow> {
+        self.inner.data_flows(action, wallet, receipt)
+    }
+
+    fn settled_flows(&self, wallet: &WalletState) -> Vec<(String, DataFlow)> {
+        self.inner.settled_flows(wallet)
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::config::{
+        BudgetConfig, ChainConfig, ColdStartConfig, ContractAddresses, ControlConfig,
+        DegradationConfig, DiversityConfig, GhostnetPluginConfig, LeaderConfig, PluginsConfig,
+        ProfileConfig, ReportConfig, RetirementConfig, ReviewConfig, SafetyConfig, ServiceConfig,
+        SimulationConfig, TriggersConfig, WalletConfig, WarmupConfig,
+    };
+    use fleet_core::validation::ConfigReport;
+    use ghostnet_actions::DeathRateTable;
+    use ghostnet_actions::config::{
+        DeadPoolSettings, ExtractStrategy, GameFilter, GasSettings, StreakProtection,
+    };
+
+    fn settings(seed: u64) -> Settings {
+        let wallet = |id: &str, byte: u8| WalletConfig {
+            id: id.into(),
+            address: Address::repeat_byte(byte),
+            profile: "test_profile".into(),
+            key_source: None,
+            private_key: None,
+            enabled: true,
+            group: None,
+            retiring: false,
+            sweep_to: None,
+            standby: false,
+            budget: BudgetConfig::default(),
+        };
+
+        Settings {
+            service: ServiceConfig::default(),
+            chain: ChainConfig {
+                chain_id: 31337,
+                rpc_url: "http://localhost:8545".into(),
+                chain_type: "mock".into(),
+                ghostnet: ContractAddresses {
+                    ghost_core: Some(Address::repeat_byte(0xC0)),
+                    hash_crash: Some(Address::repeat_byte(0xC1)),
+                    arcade_core: Some(Address::repeat_byte(0xC2)),
+                    data_token: Some(Address::repeat_byte(0xDA)),
+                    dead_pool: None,
+                },
+                ..ChainConfig::default()
+            },
+            chains: HashMap::new(),
+            selected_chain: None,
+            wallets: vec![wallet("wallet_1", 1), wallet("wallet_2", 2)],
+            plugins: PluginsConfig {
+                enabled: vec!["ghostnet".into()],
+                ghostnet: Some(GhostnetPluginConfig {
+                    min_stake: "1000000000000000000".into(),
+                    hashcrash_enabled: false,
+                    max_balance_age_secs: 60,
+                    hashcrash_loss_streak: 3,
+                    hashcrash_loss_streak_bet_pct: 0.5,
+                    death_reduction_boosts: true,
+                    yield_multiplier_boosts: true,
+                    max_boost_spend: "50000000000000000000".into(),
+                    max_boost_reward_share: 0.5,
+                    data_reserve: "0".into(),
+                    death_rates: DeathRateTable::new(),
+                    min_extract_edge_bps: 500,
+                    min_net_extract: "1000000000000000000".into(),
+                    extract_gas_cost: "0".into(),
+                    imminent_cull_risk_bps: 5000,
+                    default_exit_toll_bps: 0,
+                    exit_toll_ttl_secs: 300,
+                    reset_danger_secs: 900,
+                    reset_timer_ttl_secs: 30,
+                    transfer_tax_ttl_secs: 3600,
+                    extract_strategy: ExtractStrategy::Full,
+                    approve_before_stake: false,
+                    reentry_probability: 0.0,
+                    streak_protection: StreakProtection::new(),
+                    arcade_enabled: true,
+                    arcade_games: GameFilter::default(),
+                    dead_pool_enabled: false,
+                    dead_pool: DeadPoolSettings::new(),
+                    gas: GasSettings::default(),
+                }),
+                ..PluginsConfig::default()
+            },
+            safety: SafetyConfig {
+                // Trip readily so breaker trips show up in a short run
+                max_consecutive_errors: 2,
+                ..SafetyConfig::default()
+            },
+            profiles: HashMap::from([("test_profile".into(), ProfileConfig::default())]),
+            groups: HashMap::new(),
+            mnemonics: HashMap::new(),
+            simulation: SimulationConfig {
+                seed,
+                days: 3,
+                revert_rate: 0.3,
+                drop_rate: 0.1,
+                ..SimulationConfig::default()
+            },
+            control: ControlConfig::default(),
+            leader: LeaderConfig::default(),
+            warmup: WarmupConfig::default(),
+            cold_start: ColdStartConfig::default(),
+            diversity: DiversityConfig::default(),
+            review: ReviewConfig::default(),
+            retirement: RetirementConfig::default(),
+            report: ReportConfig::default(),
+            triggers: TriggersConfig::default(),
+            degradation: DegradationConfig::default(),
+            fleets: BTreeMap::new(),
+            load_issues: ConfigReport::new(),
+        }
+    }
+
+    async fn simulate(seed: u64) -> SimulationReport {
+        Box::pin(SimulationEngine::new(settings(seed)).unwrap().run()).await
+    }
+
+    #[tokio::test]
+    async fn same_seed_same_report() {
+        let first = simulate(7).await;
+
+        assert_eq!(first, simulate(7).await);
+        assert_eq!(first.end - first.start, chrono::Duration::days(3));
+        assert_eq!(first.actions_per_wallet.len(), 2);
+        assert!(first.actions_by_type.contains_key("ghostnet.jack_in"));
+        assert!(first.gas_spent_wei > 0);
+        // Dropped transactions are cancelled rather than left pending
+        assert!(first.replacements_by_outcome.contains_key("cancelled"));
+    }
+
+    #[tokio::test]
+    async fn different_seeds_differ() {
+        assert_ne!(simulate(1).await, simulate(2).await);
+    }
+
+    #[tokio::test]
+    async fn runs_log_no_keys_or_raw_transactions() {
+        use fleet_core::sensitive::redact;
+        use tracing_subscriber::prelude::*;
+
+        use crate::logging::Captured;
+
+        let settings = settings(7);
+        let keys: Vec<String> = (0..u32::try_from(settings.wallets.len()).unwrap())
+            .map(|index| crate::signer::development_key(index).unwrap())
+            .collect();
+
+        // Captured as written, unredacted, to see that nothing needs redacting
+        let captured = Captured::default();
+        let writer = captured.clone();
+        let subscriber = tracing_subscriber::registry().with(
+            tracing_subscriber::fmt::layer()
+                .with_ansi(false)
+                .with_writer(move || writer.clone())
+                .with_filter(tracing_subscriber::filter::LevelFilter::DEBUG),
+        );
+        let _guard = tracing::subscriber::set_default(subscriber);
+        let report = Box::pin(SimulationEngine::new(settings).unwrap().run()).await;
+        // Cancel transactions were signed and sent
+        assert!(report.replacements_by_outcome.contains_key("cancelled"));
+
+        let logs = captured.text();
+        assert!(logs.contains("Simulation finished"));
+        for key in &keys {
+            assert!(!logs.contains(key.as_str()), "a wallet key was logged");
+        }
+        let flagged: Vec<&str> = logs.lines().filter(|line| redact(line) != *line).collect();
+        assert!(flagged.is_empty(), "{flagged:#?}");
+    }
+}
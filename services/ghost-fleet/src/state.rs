@@ -0,0 +1,202 @@
+//! Fleet state that outlives the process.
+//!
+//! With `service.state_file` set, the service saves a [`FleetState`] every
+//! `service.state_save_interval_secs` and on shutdown, and picks it up again
+//! at startup: the wallets' states, with their next action, AFK period, spend
+//! ledger and warm-up or retirement, and the circuit breaker's error counts
+//! and trips. Tripped wallets stay tripped for the rest of their cooldown,
+//! counted from when they tripped, and AFK periods that run past the restart
+//! are kept; overdue wallets go through the cold-start ramp.
+//!
+//! The file is JSON, written to a temporary file first and renamed over the
+//! old one, so a crash mid-save leaves the previous state intact.
+//!
+//! # Versions
+//!
+//! - 1: the bare map of wallet states by ID.
+//! - 2: a [`FleetState`], adding the circuit breaker.
+//!
+//! Older files load with defaults for what they lack.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use fleet_core::safety::BreakerSnapshot;
+use fleet_core::wallet::WalletState;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{FleetServiceError, Result};
+
+/// Version of the state files written.
+pub const STATE_VERSION: u32 = 2;
+
+/// Saved state of a fleet.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FleetState {
+    /// Format version, see the [module docs](self).
+    pub version: u32,
+
+    /// When the state was saved, unknown for version 1 files.
+    #[serde(default)]
+    pub saved_at: Option<DateTime<Utc>>,
+
+    /// Wallet states by wallet ID.
+    pub wallets: HashMap<String, WalletState>,
+
+    /// Error counts and trips of the circuit breaker.
+    #[serde(default)]
+    pub circuit_breaker: BreakerSnapshot,
+}
+
+impl FleetState {
+    /// State of the current version, saved at `saved_at`.
+    #[must_use]
+    pub const fn new(
+        saved_at: DateTime<Utc>,
+        wallets: HashMap<String, WalletState>,
+        circuit_breaker: BreakerSnapshot,
+    ) -> Self {
+        Self {
+            version: STATE_VERSION,
+            saved_at: Some(saved_at),
+            wallets,
+            circuit_breaker,
+        }
+    }
+
+    /// Parse a state file of any version.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `json` is not a state file.
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        Ok(match serde_json::from_str(json)? {
+            StateFile::Versioned(state) => state,
+            StateFile::Wallets(wallets) => Self {
+                version: 1,
+                saved_at: None,
+                wallets,
+                circuit_breaker: BreakerSnapshot::default(),
+            },
+        })
+    }
+}
+
+/// A state file of any version.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StateFile {
+    /// Version 2 and later.
+    Versioned(FleetState),
+
+    /// Version 1.
+    Wallets(HashMap<String, WalletState>),
+}
+
+/// Load the state saved at `path`, `None` if there is none yet.
+///
+/// # Errors
+///
+/// Returns an error if the file exists but cannot be read or parsed.
+pub fn load(path: &Path) -> Result<Option<FleetState>> {
+    let error =
+        |e: &dyn std::fmt::Display| FleetServiceError::State(format!("{}: {e}", path.display()));
+    let json = match std::fs::read_to_string(path) {
+        Ok(json) => json,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(error(&e)),
+    };
+    FleetState::from_json(&json)
+        .map(Some)
+        .map_err(|e| error(&e))
+}
+
+/// Save `state` to `path`, replacing what was saved before.
+///
+/// # Errors
+///
+/// Returns an error if the file or its directory cannot be written.
+pub fn save(path: &Path, state: &FleetState) -> Result<()> {
+    let io = |e: std::io::Error| FleetServiceError::State(format!("{}: {e}", path.display()));
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir).map_err(io)?;
+    }
+
+    let json =
+        serde_json::to_string_pretty(state).map_err(|e| FleetServiceError::State(e.to_string()))?;
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    std::fs::write(&tmp, json + "\n").map_err(io)?;
+    std::fs::rename(&tmp, path).map_err(io)
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use alloy::primitives::Address;
+    use chrono::TimeZone;
+
+    fn noon() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 1, 1, 12, 0, 0).unwrap()
+    }
+
+    fn wallets() -> HashMap<String, WalletState> {
+        let mut wallet = WalletState::new("w1".into(), Address::repeat_byte(1));
+        wallet.afk_until = Some(noon() + chrono::Duration::hours(2));
+        HashMap::from([("w1".to_string(), wallet)])
+    }
+
+    #[test]
+    fn round_trips_through_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("state").join("fleet.json");
+        let breaker = BreakerSnapshot {
+            trip_times: [("w1".to_string(), noon())].into(),
+            total_trips: 1,
+            ..BreakerSnapshot::default()
+        };
+
+        assert!(load(&path).unwrap().is_none());
+        save(&path, &FleetState::new(noon(), wallets(), breaker.clone())).unwrap();
+        let state = load(&path).unwrap().unwrap();
+
+        assert_eq!(state.version, STATE_VERSION);
+        assert_eq!(state.saved_at, Some(noon()));
+        assert_eq!(state.circuit_breaker, breaker);
+        assert_eq!(
+            state.wallets["w1"].afk_until,
+            Some(noon() + chrono::Duration::hours(2))
+        );
+        assert!(!dir.path().join("state").join("fleet.json.tmp").exists());
+    }
+
+    #[test]
+    fn loads_version_1_wallet_map() {
+        let json = serde_json::to_string(&wallets()).unwrap();
+
+        let state = FleetState::from_json(&json).unwrap();
+        assert_eq!(state.version, 1);
+        assert_eq!(state.saved_at, None);
+        assert_eq!(state.circuit_breaker, BreakerSnapshot::default());
+        assert_eq!(
+            state.wallets["w1"].afk_until,
+            Some(noon() + chrono::Duration::hours(2))
+        );
+    }
+
+    #[test]
+    fn missing_breaker_defaults() {
+        let json = serde_json::json!({ "version": 2, "wallets": wallets() }).to_string();
+
+        let state = FleetState::from_json(&json).unwrap();
+        assert_eq!(state.version, 2);
+        assert_eq!(state.circuit_breaker, BreakerSnapshot::default());
+        assert!(FleetState::from_json("[1, 2]").is_err());
+    }
+}
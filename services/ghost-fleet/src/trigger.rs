@@ -0,0 +1,900 @@
+//! Early wakeups of wallets on protocol events.
+//!
+//! Wallets act on their own timers, which can leave them asleep through what
+//! they would react to: a scan that just ran at their level, a DeadPool round
+//! that opened, their position getting culled. With `triggers.enabled` set,
+//! the service takes the [`ProtocolEvent`]s of an event source, and the
+//! [`EventTrigger`] picks the wallets each one wakes:
+//!
+//! | Event | Wallets woken |
+//! |-------|---------------|
+//! | `ScanExecuted` | Wallets with a live position at the scanned level |
+//! | `RoundCreated` | Wallets not retiring that can afford one of `triggers.bet_actions` |
+//! | `PositionCulled` | The culled wallet |
+//!
+//! Each target wakes with the probability of its event's
+//! [rule](WakeRuleConfig)'s `sample_rate`, after a delay drawn on its own
+//! between `min_delay_secs` and `max_delay_secs`, so the fleet never reacts to
+//! an event in unison.
+//!
+//! A wakeup only moves a wallet's next action earlier. The woken wallet is
+//! then processed like any other due wallet, with breakers, limits, budgets,
+//! cooldowns and active hours applied; wallets that are AFK, whose circuit
+//! breaker is tripped or whose actions await review are not woken at all. The
+//! one exception is `triggers.urgent_actions`: a wallet woken outside its
+//! active hours may still run those exit actions, and nothing else.
+//!
+//! # Sources
+//!
+//! With `triggers.indexer_ws_url` set, events come from the indexer's event
+//! stream ([`IndexerStream`]), subscribed to its `scans`, `market` and
+//! `positions` topics and resumed where it left off after a reconnect.
+//! Otherwise the logs of GhostCore, `triggers.trace_scan` and
+//! `triggers.dead_pool` are polled every `triggers.poll_interval_secs`
+//! ([`LogPoller`]), from the chain's head at startup on.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+
+use alloy::primitives::{Address, U256};
+use alloy::rpc::types::Log;
+use alloy::sol_types::SolEvent;
+use chrono::{DateTime, Utc};
+use evm_provider::{ExtendedChainProvider, LogFilter};
+use fleet_core::clock::SharedClock;
+use fleet_core::plugins::{ActionId, ActionPlugin};
+use fleet_core::wallet::WalletState;
+use futures::{SinkExt, StreamExt};
+use ghostnet_abi::dead_pool::RoundCreated;
+use ghostnet_abi::ghost_core::PositionCulled;
+use ghostnet_abi::trace_scan::ScanExecuted;
+use ghostnet_actions::{GhostnetState, Level, PLUGIN_ID};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::Deserialize;
+use tokio::sync::{mpsc, watch};
+use tokio_tungstenite::tungstenite::{Error as WsError, Message};
+use tracing::{debug, info, warn};
+
+use crate::config::{TriggersConfig, WakeRuleConfig};
+use crate::error::{FleetServiceError, Result};
+
+/// Events queued for the service before the source waits for it.
+const EVENT_CAPACITY: usize = 256;
+
+/// Delay before reconnecting to the indexer, doubling up to
+/// [`MAX_RECONNECT_DELAY`] while it stays unreachable.
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+/// Longest delay before reconnecting to the indexer.
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(60);
+
+/// Topics of the indexer's event stream that carry the events.
+const TOPICS: [&str; 3] = ["scans", "market", "positions"];
+
+/// Schema version of the indexer's events subscribed to.
+const SCHEMA_VERSION: u32 = 1;
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// EVENTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// An on-chain event that can wake wallets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProtocolEvent {
+    /// A scan was executed.
+    ScanExecuted {
+        /// Level scanned.
+        level: Level,
+    },
+    /// A DeadPool round was created.
+    RoundCreated {
+        /// Round identifier.
+        round_id: U256,
+    },
+    /// A position was culled to make room for another.
+    PositionCulled {
+        /// Owner of the culled position.
+        victim: Address,
+    },
+}
+
+impl ProtocolEvent {
+    /// Name of the event, as the contract emits it.
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::ScanExecuted { .. } => "ScanExecuted",
+            Self::RoundCreated { .. } => "RoundCreated",
+            Self::PositionCulled { .. } => "PositionCulled",
+        }
+    }
+
+    /// Decode a log, if it is one of the events.
+    fn decode(log: &Log) -> Option<Self> {
+        let log = &log.inner;
+        let event = match log.topics().first().copied()? {
+            ScanExecuted::SIGNATURE_HASH => Self::ScanExecuted {
+                level: Level::from_u8(ScanExecuted::decode_log(log).ok()?.data.level)?,
+            },
+            RoundCreated::SIGNATURE_HASH => Self::RoundCreated {
+                round_id: RoundCreated::decode_log(log).ok()?.data.roundId,
+            },
+            PositionCulled::SIGNATURE_HASH => Self::PositionCulled {
+                victim: PositionCulled::decode_log(log).ok()?.data.victim,
+            },
+            _ => return None,
+        };
+        Some(event)
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// EVENT TRIGGER
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// A wallet to wake, and when it acts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Wakeup {
+    /// Wallet woken.
+    pub wallet_id: String,
+
+    /// New time of the wallet's next action.
+    pub at: DateTime<Utc>,
+}
+
+/// Picks the wallets a [`ProtocolEvent`] wakes, and when they act.
+#[derive(Debug)]
+pub struct EventTrigger {
+    /// Trigger settings.
+    config: TriggersConfig,
+
+    /// Actions that place a bet.
+    bet_actions: Vec<ActionId>,
+
+    /// Exit actions woken wallets may run outside their active hours.
+    urgent_actions: HashSet<ActionId>,
+
+    /// Draws of the sampling and the delays.
+    rng: StdRng,
+
+    /// Source of the current time.
+    clock: SharedClock,
+}
+
+impl EventTrigger {
+    /// Create a trigger with `config`'s rules, seeded with `seed` if given.
+    #[must_use]
+    pub fn new(config: &TriggersConfig, clock: SharedClock, seed: Option<u64>) -> Self {
+        Self {
+            config: config.clone(),
+            bet_actions: config.bet_actions.iter().map(ActionId::new).collect(),
+            urgent_actions: config.urgent_actions.iter().map(ActionId::new).collect(),
+            rng: seed.map_or_else(StdRng::from_os_rng, StdRng::seed_from_u64),
+            clock,
+        }
+    }
+
+    /// The rule of `event`.
+    const fn rule(&self, event: &ProtocolEvent) -> &WakeRuleConfig {
+        match event {
+            ProtocolEvent::ScanExecuted { .. } => &self.config.scan_executed,
+            ProtocolEvent::RoundCreated { .. } => &self.config.round_created,
+            ProtocolEvent::PositionCulled { .. } => &self.config.position_culled,
+        }
+    }
+
+    /// Check whether `event` concerns `wallet`, given the enabled `plugins`.
+    #[must_use]
+    pub fn targets(
+        &self,
+        event: &ProtocolEvent,
+        wallet: &WalletState,
+        plugins: &[Arc<dyn ActionPlugin>],
+    ) -> bool {
+        match event {
+            ProtocolEvent::ScanExecuted { level } => position_level(wallet) == Some(*level),
+            ProtocolEvent::RoundCreated { .. } => {
+                !wallet.is_retiring() && self.can_bet(wallet, plugins)
+            }
+            ProtocolEvent::PositionCulled { victim } => wallet.address == *victim,
+        }
+    }
+
+    /// Check whether `wallet` can afford any of the bet actions.
+    fn can_bet(&self, wallet: &WalletState, plugins: &[Arc<dyn ActionPlugin>]) -> bool {
+        self.bet_actions.iter().any(|action| {
+            plugins.iter().any(|plugin| {
+                plugin.available_actions().contains(action)
+                    && plugin.requirements(action).balances_met_by(wallet)
+            })
+        })
+    }
+
+    /// Draw the wakeups of `event` among `candidates`.
+    ///
+    /// Each candidate the event [targets](Self::targets) is woken with the
+    /// rule's sample rate, at a delay of its own within the rule's window.
+    /// Candidates are drawn for in the order given, which seeded runs should
+    /// keep stable.
+    pub fn wakeups<'a>(
+        &mut self,
+        event: &ProtocolEvent,
+        candidates: impl IntoIterator<Item = &'a WalletState>,
+        plugins: &[Arc<dyn ActionPlugin>],
+    ) -> Vec<Wakeup> {
+        let rule = *self.rule(event);
+        if !rule.enabled {
+            return Vec::new();
+        }
+
+        let now = self.clock.now();
+        let mut wakeups = Vec::new();
+        for wallet in candidates {
+            if !self.targets(event, wallet, plugins) || !self.rng.random_bool(rule.sample_rate) {
+                continue;
+            }
+            let delay = self
+                .rng
+                .random_range(rule.min_delay_secs..=rule.max_delay_secs);
+            let delay =
+                i64::try_from(delay).map_or(chrono::Duration::MAX, chrono::Duration::seconds);
+            wakeups.push(Wakeup {
+                wallet_id: wallet.id.clone(),
+                at: now + delay,
+            });
+        }
+        wakeups
+    }
+
+    /// Check whether a woken wallet may run `action` of `plugin` outside its
+    /// active hours: the action is urgent and only winds the wallet down.
+    #[must_use]
+    pub fn is_urgent(&self, plugin: &dyn ActionPlugin, action: &ActionId) -> bool {
+        self.urgent_actions.contains(action) && plugin.is_exit_action(action)
+    }
+
+    /// Check whether any action may run outside active hours at all.
+    #[must_use]
+    pub fn has_urgent_actions(&self) -> bool {
+        !self.urgent_actions.is_empty()
+    }
+}
+
+/// Level of the wallet's live GHOSTNET position, if it has one.
+fn position_level(wallet: &WalletState) -> Option<Level> {
+    let state: GhostnetState = wallet.get_plugin_state(PLUGIN_ID).ok().flatten()?;
+    state
+        .position
+        .filter(|position| position.alive)
+        .map(|position| position.level)
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// SOURCES
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Start the event source `config` asks for on a task of its own.
+///
+/// Events come from the indexer if `config.indexer_ws_url` is set, otherwise
+/// from the logs of `ghost_core` and the configured contracts, read through
+/// `provider`. The task stops on shutdown or once the returned receiver is
+/// dropped.
+pub fn spawn_source<P: ExtendedChainProvider>(
+    config: &TriggersConfig,
+    ghost_core: Option<Address>,
+    provider: P,
+    shutdown: watch::Receiver<bool>,
+) -> mpsc::Receiver<ProtocolEvent> {
+    let (events, receiver) = mpsc::channel(EVENT_CAPACITY);
+    if let Some(url) = &config.indexer_ws_url {
+        info!(url, "Waking wallets on the indexer's events");
+        tokio::spawn(IndexerStream::new(url.clone()).run(events, shutdown));
+        return receiver;
+    }
+
+    let addresses: Vec<_> = [ghost_core, config.trace_scan, config.dead_pool]
+        .into_iter()
+        .flatten()
+        .collect();
+    if addresses.is_empty() {
+        warn!("No contract addresses to poll logs of; no events wake wallets");
+        return receiver;
+    }
+    info!(?addresses, "Waking wallets on polled logs");
+    let interval = Duration::from_secs(config.poll_interval_secs);
+    tokio::spawn(LogPoller::new(provider, addresses, interval).run(events, shutdown));
+    receiver
+}
+
+/// Message of the indexer's event stream.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ServerMessage {
+    /// An event of a topic subscribed to.
+    Event {
+        /// Topic of the event.
+        topic: String,
+        /// Position of the event within its topic.
+        sequence: u64,
+        /// The event's envelope.
+        event: WireEnvelope,
+    },
+    /// The events to replay are no longer kept.
+    ReplayUnavailable {
+        /// Topic subscribed to.
+        topic: String,
+        /// Sequence after which live events follow.
+        current_sequence: u64,
+    },
+    /// A message of ours was rejected.
+    Error {
+        /// Why it was rejected.
+        message: String,
+    },
+    /// Any other message, such as the end of a replay.
+    #[serde(other)]
+    Other,
+}
+
+/// Envelope of an event, of which only the event is read.
+#[derive(Debug, Deserialize)]
+struct WireEnvelope {
+    /// The event.
+    event: WireEvent,
+}
+
+/// Event of the indexer's `scans`, `market` or `positions` topic, of which
+/// only what wakes wallets is read.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type")]
+enum WireEvent {
+    /// A scan was executed.
+    ScanExecuted {
+        /// Level scanned.
+        level: Level,
+    },
+    /// A DeadPool round was created.
+    RoundCreated {
+        /// Round identifier, as a decimal string.
+        round_id: String,
+    },
+    /// A position was culled.
+    PositionCulled {
+        /// Owner of the culled position.
+        victim: Address,
+    },
+    /// Any other event of the topics.
+    #[serde(other)]
+    Other,
+}
+
+impl WireEvent {
+    /// The protocol event, if this is one.
+    fn into_protocol_event(self) -> Option<ProtocolEvent> {
+        match self {
+            Self::ScanExecuted { level } => Some(ProtocolEvent::ScanExecuted { level }),
+            Self::RoundCreated { round_id } => Some(ProtocolEvent::RoundCreated {
+                round_id: round_id.parse().ok()?,
+            }),
+            Self::PositionCulled { victim } => Some(ProtocolEvent::PositionCulled { victim }),
+            Self::Other => None,
+        }
+    }
+}
+
+/// Events of the indexer's WebSocket event stream.
+///
+/// Subscribes to live events only at first, and resumes each topic from the
+/// last sequence it saw after a reconnect.
+#[derive(Debug)]
+pub struct IndexerStream {
+    /// WebSocket URL of the event stream.
+    url: String,
+
+    /// Last sequence seen of each topic.
+    sequences: HashMap<String, u64>,
+}
+
+impl IndexerStream {
+    /// Create a stream of the events at `url`.
+    #[must_use]
+    pub fn new(url: String) -> Self {
+        Self {
+            url,
+            sequences: HashMap::new(),
+        }
+    }
+
+    /// Send the stream's events to `events` until shutdown, reconnecting
+    /// whenever the connection is lost.
+    pub async fn run(
+        mut self,
+        events: mpsc::Sender<ProtocolEvent>,
+        mut shutdown: watch::Receiver<bool>,
+    ) {
+        let mut delay = RECONNECT_DELAY;
+        loop {
+            tokio::select! {
+                streamed = self.stream(&events) => match streamed {
+                    Ok(()) => {
+                        debug!("Event stream closed");
+                        delay = RECONNECT_DELAY;
+                    }
+                    Err(e) => {
+                        warn!(error = %e, retry_in = ?delay, "Event stream failed");
+                        delay = (delay * 2).min(MAX_RECONNECT_DELAY);
+                    }
+                },
+                _ = shutdown.wait_for(|stop| *stop) => return,
+            }
+            if events.is_closed() {
+                return;
+            }
+            tokio::select! {
+                () = tokio::time::sleep(delay) => {}
+                _ = shutdown.wait_for(|stop| *stop) => return,
+            }
+        }
+    }
+
+    /// Connect, subscribe, and send the events received until the
+    /// connection or `events` closes.
+    async fn stream(&mut self, events: &mpsc::Sender<ProtocolEvent>) -> Result<()> {
+        let url = self.url.clone();
+        let failed = |e: WsError| FleetServiceError::Trigger(format!("{url}: {e}"));
+        let (mut socket, _) = tokio_tungstenite::connect_async(url.as_str())
+            .await
+            .map_err(failed)?;
+        info!(url, "Connected to the indexer's event stream");
+
+        for topic in TOPICS {
+            let subscribe = serde_json::json!({
+                "type": "subscribe",
+                "topic": topic,
+                "from_sequence": self.sequences.get(topic).map(|sequence| sequence + 1),
+                "schema_version": SCHEMA_VERSION,
+            });
+            socket
+                .send(Message::Text(subscribe.to_string().into()))
+                .await
+                .map_err(failed)?;
+        }
+
+        while let Some(message) = socket.next().await {
+            let Message::Text(text) = message.map_err(failed)? else {
+                continue;
+            };
+            if let Some(event) = self.receive(&text)
+                && events.send(event).await.is_err()
+            {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Read a message of the stream, returning the protocol event it
+    /// carries, if any.
+    fn receive(&mut self, text: &str) -> Option<ProtocolEvent> {
+        match serde_json::from_str(text) {
+            Ok(ServerMessage::Event {
+                topic,
+                sequence,
+                event,
+            }) => {
+                self.sequences.insert(topic, sequence);
+                event.event.into_protocol_event()
+            }
+            Ok(ServerMessage::ReplayUnavailable {
+                topic,
+                current_sequence,
+            }) => {
+                debug!(topic, current_sequence, "Missed events are no longer kept");
+                self.sequences.insert(topic, current_sequence);
+                None
+            }
+            Ok(ServerMessage::Error { message }) => {
+                warn!(message, "Event stream rejected a message");
+                None
+            }
+            Ok(ServerMessage::Other) => None,
+            Err(e) => {
+                debug!(error = %e, "Unreadable event stream message");
+                None
+            }
+        }
+    }
+}
+
+/// Events of the chain's logs, polled through a provider.
+#[derive(Debug)]
+pub struct LogPoller<P> {
+    /// Provider the logs are read through.
+    provider: P,
+
+    /// Contracts whose logs are read.
+    addresses: Vec<Address>,
+
+    /// Time between two polls.
+    interval: Duration,
+}
+
+impl<P: ExtendedChainProvider> LogPoller<P> {
+    /// Create a poller of the logs of `addresses`, every `interval`.
+    #[must_use]
+    pub const fn new(provider: P, addresses: Vec<Address>, interval: Duration) -> Self {
+        Self {
+            provider,
+            addresses,
+            interval,
+        }
+    }
+
+    /// Send the events of the blocks after the current head to `events`
+    /// until shutdown.
+    pub async fn run(
+        self,
+        events: mpsc::Sender<ProtocolEvent>,
+        mut shutdown: watch::Receiver<bool>,
+    ) {
+        if !self.provider.supports_cursor_pagination() {
+            warn!("The chain provider cannot query logs; no events wake wallets");
+            return;
+        }
+
+        let mut polls = tokio::time::interval(self.interval);
+        let mut next_block = None;
+        loop {
+            tokio::select! {
+                _ = polls.tick() => {}
+                _ = shutdown.wait_for(|stop| *stop) => return,
+            }
+            match self.poll(next_block).await {
+                Ok((head, found)) => {
+                    next_block = Some(head + 1);
+                    for event in found {
+                        if events.send(event).await.is_err() {
+                            return;
+                        }
+                    }
+                }
+                Err(e) => warn!(error = %e, "Failed to poll logs for events"),
+            }
+        }
+    }
+
+    /// The events of the blocks from `from` up to the head, and the head.
+    ///
+    /// Without `from`, only the head is read.
+    async fn poll(&self, from: Option<u64>) -> evm_provider::Result<(u64, Vec<ProtocolEvent>)> {
+        let head = self.provider.get_block_number().await?;
+        let Some(from) = from else {
+            return Ok((head, Vec::new()));
+        };
+        // An endpoint behind the blocks already polled has nothing new
+        if from > head {
+            return Ok((from - 1, Vec::new()));
+        }
+        let filter = LogFilter::new(from, head).with_addresses(self.addresses.clone());
+        let logs = self.provider.get_all_logs(&filter).await?;
+        Ok((
+            head,
+            logs.iter().filter_map(ProtocolEvent::decode).collect(),
+        ))
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use alloy::primitives::TxHash;
+    use async_trait::async_trait;
+    use fleet_core::clock::{Clock, VirtualClock};
+    use fleet_core::plugins::{Action, ActionRequirements, ActionResult, PluginContext};
+    use fleet_core::profiles::BehaviorProfile;
+    use ghostnet_actions::Position;
+
+    use super::*;
+
+    /// Plugin whose bet needs 1 wei of native balance.
+    #[derive(Debug)]
+    struct BettingPlugin;
+
+    #[async_trait]
+    impl ActionPlugin for BettingPlugin {
+        fn id(&self) -> &'static str {
+            "stub"
+        }
+
+        fn name(&self) -> &'static str {
+            "Stub"
+        }
+
+        fn available_actions(&self) -> Vec<ActionId> {
+            vec![ActionId::new("stub.bet"), ActionId::new("stub.exit")]
+        }
+
+        fn requirements(&self, action: &ActionId) -> ActionRequirements {
+            let mut requirements = ActionRequirements::none();
+            if action.as_str() == "stub.bet" {
+                requirements.min_native_balance = U256::from(1);
+            }
+            requirements
+        }
+
+        fn is_exit_action(&self, action: &ActionId) -> bool {
+            action.as_str() == "stub.exit"
+        }
+
+        async fn decide_action(
+            &self,
+            _wallet: &WalletState,
+            _profile: &BehaviorProfile,
+            _context: &mut PluginContext<'_>,
+        ) -> fleet_core::Result<Option<Action>> {
+            Ok(None)
+        }
+
+        async fn execute_action(
+            &self,
+            _action: &Action,
+            _wallet: &WalletState,
+            _nonce: u64,
+        ) -> fleet_core::Result<ActionResult> {
+            Ok(ActionResult::success(TxHash::ZERO))
+        }
+
+        async fn read_state(&self, _address: Address) -> fleet_core::Result<serde_json::Value> {
+            Ok(serde_json::Value::Null)
+        }
+    }
+
+    fn plugins() -> Vec<Arc<dyn ActionPlugin>> {
+        vec![Arc::new(BettingPlugin)]
+    }
+
+    fn seeded(config: &TriggersConfig) -> (EventTrigger, Arc<VirtualClock>) {
+        let clock = Arc::new(VirtualClock::new(Utc::now()));
+        (EventTrigger::new(config, clock.clone(), Some(7)), clock)
+    }
+
+    /// Wallet `i`, with a live position at `level` if given.
+    fn wallet(i: u16, level: Option<Level>) -> WalletState {
+        let mut address = [0u8; 20];
+        address[18..].copy_from_slice(&i.to_be_bytes());
+        let mut wallet = WalletState::new(format!("w{i:04}"), Address::from(address));
+        if let Some(level) = level {
+            let state = GhostnetState {
+                position: Some(Position {
+                    amount: U256::from(100),
+                    level,
+                    entry_timestamp: 0,
+                    last_add_timestamp: 0,
+                    alive: true,
+                    ghost_streak: 0,
+                    pending_rewards: U256::ZERO,
+                    effective_death_rate_bps: 0,
+                    in_lock_period: false,
+                    active_boosts: Vec::new(),
+                }),
+                ..GhostnetState::default()
+            };
+            wallet.set_plugin_state(PLUGIN_ID, &state).unwrap();
+        }
+        wallet
+    }
+
+    #[test]
+    fn scans_wake_only_wallets_at_the_scanned_level() {
+        let (mut trigger, clock) = seeded(&TriggersConfig::default());
+        let wallets: Vec<_> = (0..100)
+            .map(|i| {
+                wallet(
+                    i,
+                    Some(if i % 2 == 0 {
+                        Level::Darknet
+                    } else {
+                        Level::Subnet
+                    }),
+                )
+            })
+            .chain([wallet(100, None)])
+            .collect();
+
+        let scan = ProtocolEvent::ScanExecuted {
+            level: Level::Darknet,
+        };
+        let wakeups = trigger.wakeups(&scan, &wallets, &plugins());
+
+        // Every target wakes at the default sample rate of 1.0
+        assert_eq!(wakeups.len(), 50);
+        for wakeup in &wakeups {
+            let i: usize = wakeup.wallet_id[1..].parse().unwrap();
+            assert_eq!(i % 2, 0, "{} is not at the scanned level", wakeup.wallet_id);
+            let delay = wakeup.at - clock.now();
+            assert!(
+                delay >= chrono::Duration::seconds(60) && delay <= chrono::Duration::seconds(600)
+            );
+        }
+    }
+
+    #[test]
+    fn wakeups_are_sampled_at_the_rule_rate() {
+        let mut config = TriggersConfig {
+            bet_actions: vec!["stub.bet".into()],
+            ..TriggersConfig::default()
+        };
+        config.round_created.sample_rate = 0.2;
+        let (mut trigger, _) = seeded(&config);
+        let wallets: Vec<_> = (0..2000)
+            .map(|i| {
+                let mut wallet = wallet(i, None);
+                wallet.set_native_balance(U256::from(1));
+                wallet
+            })
+            .collect();
+
+        let round = ProtocolEvent::RoundCreated {
+            round_id: U256::from(1),
+        };
+        let woken = trigger.wakeups(&round, &wallets, &plugins()).len();
+
+        // 400 expected, with a standard deviation of about 18
+        assert!((320..=480).contains(&woken), "{woken} of 2000 woken");
+    }
+
+    #[test]
+    fn wakeups_are_spread_over_the_window() {
+        let (mut trigger, clock) = seeded(&TriggersConfig::default());
+        let wallets: Vec<_> = (0..500).map(|i| wallet(i, Some(Level::BlackIce))).collect();
+
+        let scan = ProtocolEvent::ScanExecuted {
+            level: Level::BlackIce,
+        };
+        let delays: Vec<_> = trigger
+            .wakeups(&scan, &wallets, &plugins())
+            .iter()
+            .map(|wakeup| (wakeup.at - clock.now()).num_seconds())
+            .collect();
+
+        assert!(delays.iter().all(|delay| (60..=600).contains(delay)));
+        // Each tenth of the window gets some of the wallets, none most of them
+        let mut tenths = [0usize; 10];
+        for delay in &delays {
+            tenths[usize::try_from((delay - 60) * 10 / 541).unwrap()] += 1;
+        }
+        assert!(
+            tenths.iter().all(|&count| (20..=80).contains(&count)),
+            "{tenths:?}"
+        );
+    }
+
+    #[test]
+    fn only_wallets_that_can_bet_wake_for_rounds() {
+        let mut config = TriggersConfig {
+            bet_actions: vec!["stub.bet".into()],
+            ..TriggersConfig::default()
+        };
+        config.round_created.sample_rate = 1.0;
+        let (mut trigger, _) = seeded(&config);
+
+        let broke = wallet(1, None);
+        let mut funded = wallet(2, None);
+        funded.set_native_balance(U256::from(1));
+        let mut retiring = funded.clone();
+        retiring.id = "retiring".into();
+        retiring.start_retiring(None, Utc::now());
+
+        let round = ProtocolEvent::RoundCreated {
+            round_id: U256::from(1),
+        };
+        let wakeups = trigger.wakeups(&round, [&broke, &funded, &retiring], &plugins());
+        assert_eq!(wakeups.len(), 1);
+        assert_eq!(wakeups[0].wallet_id, funded.id);
+    }
+
+    #[test]
+    fn culls_wake_the_victim_unless_disabled() {
+        let mut config = TriggersConfig::default();
+        let (mut trigger, _) = seeded(&config);
+        let wallets = [wallet(1, Some(Level::Vault)), wallet(2, Some(Level::Vault))];
+        let cull = ProtocolEvent::PositionCulled {
+            victim: wallets[1].address,
+        };
+
+        let wakeups = trigger.wakeups(&cull, &wallets, &plugins());
+        assert_eq!(wakeups.len(), 1);
+        assert_eq!(wakeups[0].wallet_id, wallets[1].id);
+
+        config.position_culled.enabled = false;
+        let (mut trigger, _) = seeded(&config);
+        assert!(trigger.wakeups(&cull, &wallets, &plugins()).is_empty());
+    }
+
+    #[test]
+    fn urgent_actions_must_be_exits() {
+        let config = TriggersConfig {
+            urgent_actions: vec!["stub.exit".into(), "stub.bet".into()],
+            ..TriggersConfig::default()
+        };
+        let (trigger, _) = seeded(&config);
+
+        assert!(trigger.is_urgent(&BettingPlugin, &ActionId::new("stub.exit")));
+        assert!(!trigger.is_urgent(&BettingPlugin, &ActionId::new("stub.bet")));
+    }
+
+    #[test]
+    fn logs_decode_into_events() {
+        let victim = Address::repeat_byte(0x11);
+        let culled = PositionCulled {
+            victim,
+            penaltyAmount: U256::from(1),
+            returnedAmount: U256::from(2),
+            newEntrant: Address::repeat_byte(0x22),
+        };
+        let scan = ScanExecuted {
+            level: 4,
+            scanId: U256::from(9),
+            seed: U256::from(3),
+            executedAt: 1_700_000_000,
+        };
+        let log = |data| Log {
+            inner: alloy::primitives::Log {
+                address: Address::ZERO,
+                data,
+            },
+            ..Log::default()
+        };
+
+        assert_eq!(
+            ProtocolEvent::decode(&log(culled.encode_log_data())),
+            Some(ProtocolEvent::PositionCulled { victim })
+        );
+        assert_eq!(
+            ProtocolEvent::decode(&log(scan.encode_log_data())),
+            Some(ProtocolEvent::ScanExecuted {
+                level: Level::Darknet
+            })
+        );
+    }
+
+    #[test]
+    fn stream_messages_are_read_and_resumed_from() {
+        let mut stream = IndexerStream::new("ws://indexer/ws".into());
+        let scan = r#"{"type":"event","topic":"scans","sequence":42,"event":{
+            "schema_version":1,"topic":"scans","sequence":42,"timestamp":"2026-01-01T00:00:00Z",
+            "event":{"type":"ScanExecuted","block":{"number":1,"hash":"0x","tx_hash":"0x","log_index":0},
+            "level":"Darknet","scan_id":"9","seed":"3","executed_at":1700000000}}}"#;
+        let round = r#"{"type":"event","topic":"market","sequence":7,"event":{
+            "event":{"type":"RoundCreated","round_id":"12","round_type":"DeathCount"}}}"#;
+        let bet = r#"{"type":"event","topic":"market","sequence":8,"event":{
+            "event":{"type":"BetPlaced","round_id":"12"}}}"#;
+
+        assert_eq!(
+            stream.receive(scan),
+            Some(ProtocolEvent::ScanExecuted {
+                level: Level::Darknet
+            })
+        );
+        assert_eq!(
+            stream.receive(round),
+            Some(ProtocolEvent::RoundCreated {
+                round_id: U256::from(12)
+            })
+        );
+        assert_eq!(stream.receive(bet), None);
+        assert_eq!(
+            stream.receive(r#"{"type":"replay_complete","topic":"scans"}"#),
+            None
+        );
+        assert_eq!(stream.receive("not json"), None);
+        assert_eq!(stream.sequences["scans"], 42);
+        assert_eq!(stream.sequences["market"], 8);
+    }
+}
@@ -0,0 +1,280 @@
+//! ArcadeCore action decision logic.
+//!
+//! This module handles decisions for:
+//! - `arcade_play`: Enter one of the games registered with ArcadeCore
+//!
+//! # Game Selection
+//!
+//! Games are ranked by their stakes (minimum entry). A profile leans towards
+//! the game whose rank matches its risk tolerance, so careful wallets mostly
+//! play the cheap games and risk tolerant ones the expensive games, while
+//! every game keeps some chance of being picked.
+
+// Allow precision loss for game ranks (small integers, not tokens)
+#![allow(clippy::cast_precision_loss)]
+// Allow suboptimal floating point ops - readability over micro-optimization
+#![allow(clippy::suboptimal_flops)]
+
+use alloy::primitives::U256;
+use fleet_core::plugins::{Action, PluginContext, checked_action_id};
+use fleet_core::profiles::BehaviorProfile;
+use rand::Rng;
+use tracing::debug;
+
+use crate::config::BehaviorSettings;
+use crate::math::{gross_for_net, net_for_gross, pct_to_bps, percentage_of, random_bps};
+use crate::state::{ArcadeGame, GhostnetState};
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// ACTION IDS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Action ID for playing an ArcadeCore game.
+pub const ACTION_ARCADE_PLAY: &str = checked_action_id("ghostnet.arcade_play");
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// CONSTANTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Largest share of the balance entered into one game (10%).
+const MAX_ENTRY_BPS: u64 = 1000;
+
+/// Weight of the game whose rank is furthest from the risk tolerance,
+/// relative to the best matching one.
+const MIN_WEIGHT: f64 = 0.1;
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// DECISION LOGIC
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Decision logic for ArcadeCore actions.
+pub struct ArcadeDecider;
+
+impl ArcadeDecider {
+    /// Decide whether to play one of `games`.
+    ///
+    /// Only games whose minimum entry fits the wallet's budget (10% of its
+    /// balance) are considered.
+    pub fn decide(
+        state: &GhostnetState,
+        games: &[ArcadeGame],
+        profile: &BehaviorProfile,
+        settings: &BehaviorSettings,
+        context: &mut PluginContext<'_>,
+    ) -> Option<Action> {
+        if !settings.plays_arcade {
+            return None;
+        }
+
+        let budget = percentage_of(state.data_balance, MAX_ENTRY_BPS);
+        let affordable: Vec<&ArcadeGame> = games
+            .iter()
+            .filter(|game| !game.min_entry.is_zero() && game.min_entry <= budget)
+            .collect();
+        if affordable.is_empty() {
+            return None;
+        }
+
+        // Same appetite for games as for HashCrash
+        let play_prob = 0.1 + (profile.activity_level / 30.0);
+        if !context.rng.random_bool(play_prob.min(0.5)) {
+            return None;
+        }
+
+        let game = Self::pick_game(&affordable, profile, context)?;
+        let amount = Self::calculate_entry(state, game, profile, settings, context);
+
+        debug!(
+            game_id = game.game_id,
+            amount = %amount,
+            probability = play_prob,
+            "Deciding to play arcade game"
+        );
+
+        Some(Action::with_data(
+            ACTION_ARCADE_PLAY,
+            "Arcade Play",
+            serde_json::json!({
+                "game_id": game.game_id,
+                "amount": amount.to_string(),
+            }),
+        ))
+    }
+
+    /// Pick a game, weighted by how well its stakes match the profile's risk
+    /// tolerance.
+    fn pick_game<'a>(
+        games: &[&'a ArcadeGame],
+        profile: &BehaviorProfile,
+        context: &mut PluginContext<'_>,
+    ) -> Option<&'a ArcadeGame> {
+        let mut ranked = games.to_vec();
+        ranked.sort_by_key(|game| (game.min_entry, game.game_id));
+
+        let last = ranked.len().saturating_sub(1).max(1) as f64;
+        let weights: Vec<f64> = (0..ranked.len())
+            .map(|rank| {
+                let distance = (rank as f64 / last - profile.risk_tolerance).abs();
+                1.0 - (1.0 - MIN_WEIGHT) * distance.min(1.0)
+            })
+            .collect();
+
+        let mut roll = context.rng.random_range(0.0..weights.iter().sum::<f64>());
+        for (game, weight) in ranked.iter().zip(&weights) {
+            if roll < *weight {
+                return Some(game);
+            }
+            roll -= weight;
+        }
+        ranked.last().copied()
+    }
+
+    /// Calculate the entry for a game.
+    ///
+    /// The wallet enters up to `max_arcade_bet_pct` of its balance scaled by
+    /// risk tolerance, clamped to the game's limits and to 10% of the balance.
+    fn calculate_entry(
+        state: &GhostnetState,
+        game: &ArcadeGame,
+        profile: &BehaviorProfile,
+        settings: &BehaviorSettings,
+        context: &mut PluginContext<'_>,
+    ) -> U256 {
+        let max_bps = pct_to_bps(settings.max_arcade_bet_pct * profile.risk_tolerance);
+
+        // Apply jitter: 30% to 100% of max
+        let jitter_bps = random_bps(0.3, 1.0, context.rng);
+        // Safe: max_bps * jitter_bps / 10000 fits in u64 (max ~10000 * 10000 / 10000 = 10000)
+        #[allow(clippy::cast_possible_truncation)]
+        let entry_bps = (u128::from(max_bps) * u128::from(jitter_bps) / 10_000) as u64;
+
+        // Sized by what lands after the transfer tax, then sent in full
+        let tax_bps = u64::from(state.transfer_tax.arcade_core_bps);
+        let spendable = net_for_gross(state.data_balance, tax_bps);
+        let amount = game.clamp_entry(percentage_of(spendable, entry_bps));
+        let amount = amount.min(percentage_of(spendable, MAX_ENTRY_BPS));
+        gross_for_net(amount, tax_bps)
+            .map_or(U256::ZERO, |gross| gross.min(game.max_entry.unwrap_or(U256::MAX)))
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::Address;
+    use chrono::Utc;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    const DATA: u128 = 1_000_000_000_000_000_000;
+
+    fn test_context(rng: &mut StdRng) -> PluginContext<'_> {
+        PluginContext::new(Utc::now(), rng, &serde_json::Value::Null)
+    }
+
+    fn game(game_id: u64, min: u128, max: Option<u128>) -> ArcadeGame {
+        ArcadeGame {
+            game_id,
+            game: Address::repeat_byte(0x10),
+            min_entry: U256::from(min),
+            max_entry: max.map(U256::from),
+            rake_bps: 300,
+        }
+    }
+
+    fn state(balance: u128) -> GhostnetState {
+        GhostnetState {
+            data_balance: U256::from(balance),
+            ..GhostnetState::default()
+        }
+    }
+
+    #[test]
+    fn risk_tolerance_weights_game_selection() {
+        let cheap = game(1, DATA, None);
+        let pricey = game(2, 50 * DATA, None);
+        let games = [&pricey, &cheap];
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut context = test_context(&mut rng);
+
+        let mut picks = |profile: &BehaviorProfile| {
+            (0..1000)
+                .filter(|_| {
+                    ArcadeDecider::pick_game(&games, profile, &mut context)
+                        .is_some_and(|game| game.game_id == pricey.game_id)
+                })
+                .count()
+        };
+        let careful = picks(&BehaviorProfile::whale());
+        let reckless = picks(&BehaviorProfile::degen());
+
+        assert!(
+            careful < 500,
+            "whale picked the pricey game {careful} times"
+        );
+        assert!(
+            reckless > 500,
+            "degen picked the pricey game {reckless} times"
+        );
+        assert!(careful > 0, "every game keeps a chance");
+    }
+
+    #[test]
+    fn entry_is_clamped_to_game_limits() {
+        let settings = BehaviorSettings::default();
+        let profile = BehaviorProfile::degen();
+        let entry = |state: &GhostnetState, game: &ArcadeGame| {
+            let mut rng = StdRng::seed_from_u64(7);
+            let mut context = test_context(&mut rng);
+            ArcadeDecider::calculate_entry(state, game, &profile, &settings, &mut context)
+        };
+
+        // Well off wallets are held to the game's maximum
+        let rich = state(100_000 * DATA);
+        assert_eq!(
+            entry(&rich, &game(1, DATA, Some(10 * DATA))),
+            U256::from(10 * DATA)
+        );
+
+        // Small wallets enter at least the minimum, up to 10% of their balance
+        let small = state(100 * DATA);
+        assert_eq!(
+            entry(&small, &game(1, 8 * DATA, None)),
+            U256::from(8 * DATA)
+        );
+        let unlimited = entry(&small, &game(1, DATA, None));
+        assert!(unlimited >= U256::from(DATA) && unlimited <= U256::from(10 * DATA));
+    }
+
+    #[test]
+    fn skips_games_beyond_budget() {
+        let settings = BehaviorSettings::default();
+        let mut profile = BehaviorProfile::degen();
+        profile.activity_level = 20.0;
+        let games = [game(1, 20 * DATA, None), game(2, 5 * DATA, Some(6 * DATA))];
+
+        for seed in 0..50 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let mut context = test_context(&mut rng);
+            let action = ArcadeDecider::decide(
+                &state(100 * DATA),
+                &games,
+                &profile,
+                &settings,
+                &mut context,
+            );
+            if let Some(action) = action {
+                assert_eq!(action.data["game_id"], 2, "game 1 needs more than 10 DATA");
+            }
+        }
+
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut context = test_context(&mut rng);
+        let poor = state(10 * DATA);
+        assert!(ArcadeDecider::decide(&poor, &games, &profile, &settings, &mut context).is_none());
+    }
+}
@@ -0,0 +1,255 @@
+//! Boost action decision logic.
+//!
+//! This module handles decisions for:
+//! - `apply_boost`: Apply a signed boost grant to the active position
+//!
+//! # Cost and Benefit
+//!
+//! GhostCore does not sell boosts; they come as grants signed by the boost
+//! signer (see [`BoostOffer`]), so the only price of a boost is what its
+//! grant cost the wallet. A profile pays at most
+//! `max_boost_reward_share * risk_tolerance` of the position's pending
+//! rewards for one boost, and applies an affordable grant with probability
+//! `risk_tolerance`: degens buy boosts eagerly, whales rarely.
+
+use alloy::primitives::U256;
+use fleet_core::plugins::{Action, PluginContext, checked_action_id};
+use fleet_core::profiles::BehaviorProfile;
+use rand::Rng;
+use tracing::debug;
+
+use crate::config::BehaviorSettings;
+use crate::math::{pct_to_bps, percentage_of};
+use crate::state::{BoostOffer, GhostnetState, Position};
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// ACTION IDS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Action ID for applying a boost to the position.
+pub const ACTION_APPLY_BOOST: &str = checked_action_id("ghostnet.apply_boost");
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// DECISION LOGIC
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Decision logic for position boosts.
+pub struct BoostDecider;
+
+impl BoostDecider {
+    /// Decide whether to apply one of the wallet's boost grants.
+    pub fn decide(
+        state: &GhostnetState,
+        profile: &BehaviorProfile,
+        settings: &BehaviorSettings,
+        context: &mut PluginContext<'_>,
+    ) -> Option<Action> {
+        let position = state.active_position()?;
+
+        #[allow(clippy::cast_sign_loss)]
+        let now_unix = context.now.timestamp() as u64;
+        let offer = Self::best_offer(state, position, profile, settings, now_unix)?;
+
+        let apply_prob = profile.risk_tolerance.clamp(0.0, 1.0);
+        if !context.rng.random_bool(apply_prob) {
+            return None;
+        }
+
+        debug!(
+            boost_type = ?offer.boost_type,
+            value_bps = offer.value_bps,
+            cost = %offer.cost,
+            probability = apply_prob,
+            "Deciding to apply boost"
+        );
+
+        Some(Action::with_data(
+            ACTION_APPLY_BOOST,
+            "Apply Boost",
+            serde_json::json!({ "offer": offer }),
+        ))
+    }
+
+    /// The cheapest grant worth applying, if any.
+    ///
+    /// Skips expired grants, disabled boost types, types the position already
+    /// has an active boost of, and grants that cost more than the profile
+    /// pays or than is left of `max_boost_spend`.
+    fn best_offer<'a>(
+        state: &'a GhostnetState,
+        position: &Position,
+        profile: &BehaviorProfile,
+        settings: &BehaviorSettings,
+        now_unix: u64,
+    ) -> Option<&'a BoostOffer> {
+        let max_cost = Self::max_cost(position, profile, settings);
+        let budget_left = U256::from(settings.max_boost_spend).saturating_sub(state.boost_spent);
+
+        state
+            .boost_offers
+            .iter()
+            .filter(|offer| offer.is_valid(now_unix))
+            .filter(|offer| settings.boost_enabled(offer.boost_type))
+            .filter(|offer| !position.has_active_boost(offer.boost_type, now_unix))
+            .filter(|offer| offer.cost <= max_cost && offer.cost <= budget_left)
+            .min_by_key(|offer| offer.cost)
+    }
+
+    /// Most a profile pays for one boost: a risk-scaled share of the
+    /// position's pending rewards.
+    fn max_cost(
+        position: &Position,
+        profile: &BehaviorProfile,
+        settings: &BehaviorSettings,
+    ) -> U256 {
+        let share = settings.max_boost_reward_share * profile.risk_tolerance;
+        percentage_of(position.pending_rewards, pct_to_bps(share))
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::state::{ActiveBoost, BoostType, Level};
+    use alloy::primitives::{B256, Bytes};
+    use chrono::Utc;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    const DATA: u128 = 1_000_000_000_000_000_000;
+
+    fn offer(boost_type: BoostType, cost: u128) -> BoostOffer {
+        BoostOffer {
+            boost_type,
+            value_bps: 1000,
+            expiry: u64::MAX,
+            nonce: B256::with_last_byte(u8::try_from(cost % 256).unwrap_or_default()),
+            signature: Bytes::from(vec![0xAB; 65]),
+            cost: U256::from(cost),
+        }
+    }
+
+    /// A live position with 100 DATA of pending rewards.
+    fn state_with(offers: Vec<BoostOffer>) -> GhostnetState {
+        GhostnetState {
+            position: Some(Position {
+                amount: U256::from(500 * DATA),
+                level: Level::Darknet,
+                entry_timestamp: 0,
+                last_add_timestamp: 0,
+                alive: true,
+                ghost_streak: 2,
+                pending_rewards: U256::from(100 * DATA),
+                effective_death_rate_bps: 3500,
+                in_lock_period: false,
+                active_boosts: Vec::new(),
+            }),
+            boost_offers: offers,
+            ..GhostnetState::default()
+        }
+    }
+
+    fn best<'a>(state: &'a GhostnetState, profile: &BehaviorProfile) -> Option<&'a BoostOffer> {
+        let settings = BehaviorSettings::default();
+        let position = state.position.as_ref()?;
+        #[allow(clippy::cast_sign_loss)]
+        let now = Utc::now().timestamp() as u64;
+        BoostDecider::best_offer(state, position, profile, &settings, now)
+    }
+
+    #[test]
+    fn cost_threshold_scales_with_risk_tolerance() {
+        // Degens pay up to 0.5 * 0.85 of pending rewards, whales 0.5 * 0.2
+        let state = state_with(vec![offer(BoostType::DeathReduction, 30 * DATA)]);
+        assert!(best(&state, &BehaviorProfile::degen()).is_some());
+        assert!(best(&state, &BehaviorProfile::whale()).is_none());
+
+        let state = state_with(vec![offer(BoostType::DeathReduction, 43 * DATA)]);
+        assert!(
+            best(&state, &BehaviorProfile::degen()).is_none(),
+            "above 42.5 DATA"
+        );
+
+        // Free grants are worth applying for everyone
+        let state = state_with(vec![offer(BoostType::DeathReduction, 0)]);
+        assert!(best(&state, &BehaviorProfile::whale()).is_some());
+    }
+
+    #[test]
+    fn picks_cheapest_affordable_offer() {
+        let state = state_with(vec![
+            offer(BoostType::DeathReduction, 20 * DATA),
+            offer(BoostType::YieldMultiplier, 5 * DATA),
+            offer(BoostType::YieldMultiplier, 90 * DATA),
+        ]);
+        let chosen = best(&state, &BehaviorProfile::degen()).unwrap();
+        assert_eq!(chosen.cost, U256::from(5 * DATA));
+    }
+
+    #[test]
+    fn does_not_rebuy_active_boost() {
+        let mut state = state_with(vec![offer(BoostType::DeathReduction, 0)]);
+        let active = ActiveBoost {
+            boost_type: BoostType::DeathReduction,
+            value_bps: 500,
+            expiry: u64::MAX,
+        };
+        state.position.as_mut().unwrap().active_boosts.push(active);
+        assert!(best(&state, &BehaviorProfile::degen()).is_none());
+
+        // Another type is still fine, as is the same type once expired
+        state
+            .boost_offers
+            .push(offer(BoostType::YieldMultiplier, 0));
+        let chosen = best(&state, &BehaviorProfile::degen()).unwrap();
+        assert_eq!(chosen.boost_type, BoostType::YieldMultiplier);
+
+        state.boost_offers.pop();
+        state.position.as_mut().unwrap().active_boosts[0].expiry = 1;
+        assert!(best(&state, &BehaviorProfile::degen()).is_some());
+    }
+
+    #[test]
+    fn respects_type_flags_and_spend_cap() {
+        let mut settings = BehaviorSettings {
+            death_reduction_boosts: false,
+            ..BehaviorSettings::default()
+        };
+        let mut state = state_with(vec![offer(BoostType::DeathReduction, DATA)]);
+        let position = state.position.clone().unwrap();
+        let profile = BehaviorProfile::degen();
+        assert!(BoostDecider::best_offer(&state, &position, &profile, &settings, 0).is_none());
+
+        settings.death_reduction_boosts = true;
+        state.boost_spent = U256::from(settings.max_boost_spend);
+        assert!(BoostDecider::best_offer(&state, &position, &profile, &settings, 0).is_none());
+
+        state.boost_spent = U256::from(settings.max_boost_spend - DATA);
+        assert!(BoostDecider::best_offer(&state, &position, &profile, &settings, 0).is_some());
+    }
+
+    #[test]
+    fn decides_only_with_live_position() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut context = PluginContext::new(Utc::now(), &mut rng, &serde_json::Value::Null);
+        let settings = BehaviorSettings::default();
+        // Risk tolerance 0.95: applies nearly every time
+        let profile = BehaviorProfile::sniper();
+
+        let mut state = state_with(vec![offer(BoostType::YieldMultiplier, 0)]);
+        let action = (0..10)
+            .find_map(|_| BoostDecider::decide(&state, &profile, &settings, &mut context))
+            .unwrap();
+        assert_eq!(action.id.as_str(), ACTION_APPLY_BOOST);
+        let chosen: BoostOffer = serde_json::from_value(action.data["offer"].clone()).unwrap();
+        assert_eq!(chosen, state.boost_offers[0]);
+
+        state.position.as_mut().unwrap().alive = false;
+        assert!(BoostDecider::decide(&state, &profile, &settings, &mut context).is_none());
+    }
+}
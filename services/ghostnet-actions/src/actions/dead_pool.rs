@@ -0,0 +1,429 @@
+//! DeadPool action decision logic.
+//!
+//! This module handles decisions for:
+//! - `dead_pool_bet`: Bet OVER or UNDER in an open prediction round
+//!
+//! # Picking a Side
+//!
+//! DeadPool is parimutuel: the winners split the pot net of a 5% rake, so a
+//! side pays more the less was bet on it. A bet's expected value is its
+//! payout times the chance of its side winning, taken from the round type's
+//! prior in [`DeadPoolSettings`]. Of the open rounds and both their sides,
+//! the bet goes where the expected value beats an even bet by the most, and
+//! only if by at least `min_edge_bps`. Betting against the crowd is what
+//! gets there: with an even prior, the side with the smaller pool.
+//!
+//! # Late Bettors
+//!
+//! Profiles at least `late_min_patience` patient wait for the last
+//! `late_window` of a round's betting window, which runs from when the
+//! plugin first saw the round open to its deadline. The pools are fuller
+//! by then, and the odds closer to where they end up. Whoever bets, the
+//! round is read again just before the bet is sent, and the bet dropped if
+//! its payout fell by more than `max_odds_drift_bps` (see
+//! [`odds_moved`](DeadPoolDecider::odds_moved)).
+
+// Allow precision loss for payouts in basis points (small integers, not tokens)
+#![allow(clippy::cast_precision_loss)]
+
+use alloy::primitives::U256;
+use fleet_core::plugins::{Action, PluginContext, checked_action_id};
+use fleet_core::profiles::BehaviorProfile;
+use rand::Rng;
+use tracing::debug;
+
+use crate::config::{BehaviorSettings, DeadPoolSettings};
+use crate::math::{AmountBounds, BPS_100_PERCENT, percentage_of, sample_bet};
+use crate::state::{DeadPoolRound, GhostnetState};
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// ACTION IDS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Action ID for placing a DeadPool bet.
+pub const ACTION_DEAD_POOL_BET: &str = checked_action_id("ghostnet.dead_pool_bet");
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// CONSTANTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Minimum bet amount (1 DATA).
+pub const MIN_BET: u128 = 1_000_000_000_000_000_000;
+
+/// Largest share of the balance bet on one round (10%).
+const MAX_BET_BPS: u64 = 1000;
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// DECISION LOGIC
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// A side of a round worth betting on.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SidePick {
+    /// Whether the bet is on OVER.
+    pub over: bool,
+
+    /// What the bet pays per DATA if it wins (basis points).
+    pub payout_bps: u64,
+
+    /// How much the bet's expected value beats an even bet by (basis
+    /// points of the bet).
+    pub edge_bps: f64,
+}
+
+/// Decision logic for DeadPool actions.
+pub struct DeadPoolDecider;
+
+impl DeadPoolDecider {
+    /// Decide whether to bet on one of the open `rounds`.
+    pub fn decide(
+        state: &GhostnetState,
+        rounds: &[DeadPoolRound],
+        profile: &BehaviorProfile,
+        settings: &BehaviorSettings,
+        context: &mut PluginContext<'_>,
+    ) -> Option<Action> {
+        if !settings.plays_dead_pool {
+            return None;
+        }
+
+        let min_bet = U256::from(MIN_BET);
+        if state.data_balance < min_bet {
+            return None;
+        }
+
+        let now = u64::try_from(context.now.timestamp()).unwrap_or_default();
+        let dead_pool = &settings.dead_pool;
+        let late = profile.patience >= dead_pool.late_min_patience;
+        let open: Vec<&DeadPoolRound> = rounds
+            .iter()
+            .filter(|round| round.can_bet(now))
+            .filter(|round| !late || Self::in_late_window(round, now, dead_pool))
+            .collect();
+        if open.is_empty() {
+            return None;
+        }
+
+        // Same appetite for rounds as for HashCrash
+        let bet_prob = 0.1 + (profile.activity_level / 30.0);
+        if !context.rng.random_bool(bet_prob.min(0.5)) {
+            return None;
+        }
+
+        let amount = Self::calculate_bet_amount(state, profile, settings, context);
+        if amount < min_bet {
+            return None;
+        }
+
+        let (round, pick) = open
+            .into_iter()
+            .filter_map(|round| Some((round, Self::pick_side(round, amount, dead_pool)?)))
+            .max_by(|(_, a), (_, b)| a.edge_bps.total_cmp(&b.edge_bps))?;
+
+        debug!(
+            round_id = round.round_id,
+            over = pick.over,
+            amount = %amount,
+            payout_bps = pick.payout_bps,
+            edge_bps = pick.edge_bps,
+            late,
+            "Deciding to place DeadPool bet"
+        );
+
+        Some(Action::with_data(
+            ACTION_DEAD_POOL_BET,
+            "DeadPool Bet",
+            serde_json::json!({
+                "round_id": round.round_id,
+                "is_over": pick.over,
+                "amount": amount.to_string(),
+                "payout_bps": pick.payout_bps,
+            }),
+        ))
+    }
+
+    /// The side of `round` a bet of `amount` is worth the most on, if its
+    /// edge reaches `min_edge_bps`.
+    #[must_use]
+    pub fn pick_side(
+        round: &DeadPoolRound,
+        amount: U256,
+        settings: &DeadPoolSettings,
+    ) -> Option<SidePick> {
+        let over_chance = settings.priors.over_chance(round.round_type)?;
+        [(true, over_chance), (false, 1.0 - over_chance)]
+            .into_iter()
+            .map(|(over, chance)| {
+                let payout_bps = round.payout_bps(over, amount);
+                SidePick {
+                    over,
+                    payout_bps,
+                    edge_bps: chance.mul_add(payout_bps as f64, -(BPS_100_PERCENT as f64)),
+                }
+            })
+            .filter(|pick| pick.edge_bps >= settings.min_edge_bps as f64)
+            .max_by(|a, b| a.edge_bps.total_cmp(&b.edge_bps))
+    }
+
+    /// Check if `round` is in the last `late_window` of its betting window
+    /// at `now`.
+    #[must_use]
+    pub fn in_late_window(round: &DeadPoolRound, now: u64, settings: &DeadPoolSettings) -> bool {
+        let window = round.deadline.saturating_sub(round.opened_at) as f64;
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)] // At most the window
+        let late = (window * settings.late_window.clamp(0.0, 1.0)) as u64;
+        now >= round.deadline.saturating_sub(late)
+    }
+
+    /// Why a bet decided at a payout of `decided_bps` is no longer worth
+    /// sending on `round` as it is now, if it is not.
+    ///
+    /// `None` means the round is closed. A payout that fell by more than
+    /// `max_odds_drift_bps` of what it was is too far.
+    #[must_use]
+    pub fn odds_moved(
+        round: Option<&DeadPoolRound>,
+        over: bool,
+        amount: U256,
+        decided_bps: u64,
+        settings: &DeadPoolSettings,
+    ) -> Option<String> {
+        let Some(round) = round else {
+            return Some("DeadPool round is closed".into());
+        };
+        let payout_bps = round.payout_bps(over, amount);
+        let drift_bps = settings.max_odds_drift_bps.min(BPS_100_PERCENT);
+        let floor = u128::from(decided_bps) * u128::from(BPS_100_PERCENT - drift_bps)
+            / u128::from(BPS_100_PERCENT);
+        (u128::from(payout_bps) < floor).then(|| {
+            format!(
+                "DeadPool round {} payout fell from {decided_bps} to {payout_bps} bps",
+                round.round_id
+            )
+        })
+    }
+
+    /// Calculate bet amount based on balance and settings.
+    ///
+    /// Sampled between the minimum bet and 10% of the balance, see
+    /// [`sample_bet`].
+    fn calculate_bet_amount(
+        state: &GhostnetState,
+        profile: &BehaviorProfile,
+        settings: &BehaviorSettings,
+        context: &mut PluginContext<'_>,
+    ) -> U256 {
+        let max = percentage_of(state.data_balance, MAX_BET_BPS);
+        let bounds = AmountBounds::new(U256::from(MIN_BET), max)
+            .with_reserve(U256::from(settings.data_reserve));
+        sample_bet(
+            profile,
+            state.data_balance,
+            settings.dead_pool.max_bet_pct,
+            &bounds,
+            context.rng,
+        )
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use chrono::{DateTime, Utc};
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    const DATA: u128 = 1_000_000_000_000_000_000;
+
+    fn data(tokens: u128) -> U256 {
+        U256::from(tokens * DATA)
+    }
+
+    /// A death count round open from 1000 to 2000 with the given pools.
+    fn round(over: u128, under: u128) -> DeadPoolRound {
+        DeadPoolRound {
+            round_id: 1,
+            round_type: 0,
+            line: U256::from(50),
+            over_pool: data(over),
+            under_pool: data(under),
+            deadline: 2000,
+            opened_at: 1000,
+        }
+    }
+
+    fn at(now: i64) -> DateTime<Utc> {
+        DateTime::from_timestamp(now, 0).unwrap()
+    }
+
+    fn funded() -> GhostnetState {
+        GhostnetState {
+            data_balance: data(1000),
+            ..GhostnetState::default()
+        }
+    }
+
+    #[test]
+    fn side_flips_with_imbalance() {
+        let settings = DeadPoolSettings::default();
+
+        // The crowd is on OVER, so UNDER pays
+        let pick = DeadPoolDecider::pick_side(&round(300, 100), data(1), &settings).unwrap();
+        assert!(!pick.over);
+        assert!(pick.payout_bps > 30_000, "{pick:?}");
+
+        // And the other way around
+        let pick = DeadPoolDecider::pick_side(&round(100, 300), data(1), &settings).unwrap();
+        assert!(pick.over);
+
+        // Balanced pools pay less than even money after the rake
+        assert_eq!(
+            DeadPoolDecider::pick_side(&round(100, 100), data(1), &settings),
+            None
+        );
+    }
+
+    #[test]
+    fn priors_outweigh_small_imbalances() {
+        let mut settings = DeadPoolSettings::default();
+        let tilted = round(130, 100);
+        assert!(
+            !DeadPoolDecider::pick_side(&tilted, data(1), &settings)
+                .unwrap()
+                .over
+        );
+
+        // OVER is likely enough to be worth its smaller payout
+        settings.priors.death_count = 0.7;
+        assert!(
+            DeadPoolDecider::pick_side(&tilted, data(1), &settings)
+                .unwrap()
+                .over
+        );
+
+        // Unknown round types are not bet on
+        let unknown = DeadPoolRound {
+            round_type: 9,
+            ..round(300, 100)
+        };
+        assert_eq!(
+            DeadPoolDecider::pick_side(&unknown, data(1), &settings),
+            None
+        );
+    }
+
+    #[test]
+    fn edge_threshold_holds_back_thin_bets() {
+        let mut settings = DeadPoolSettings::default();
+        let tilted = round(130, 100);
+        assert!(DeadPoolDecider::pick_side(&tilted, data(1), &settings).is_some());
+        settings.min_edge_bps = 2000;
+        assert_eq!(
+            DeadPoolDecider::pick_side(&tilted, data(1), &settings),
+            None
+        );
+    }
+
+    #[test]
+    fn late_bettors_wait_for_the_end_of_the_window() {
+        let state = funded();
+        let rounds = [round(300, 100)];
+        let settings = BehaviorSettings::default();
+        let bets = |profile: &BehaviorProfile, now: i64| {
+            (0..50)
+                .filter(|seed| {
+                    let mut rng = StdRng::seed_from_u64(*seed);
+                    let mut context =
+                        PluginContext::new(at(now), &mut rng, &serde_json::Value::Null);
+                    DeadPoolDecider::decide(&state, &rounds, profile, &settings, &mut context)
+                        .is_some()
+                })
+                .count()
+        };
+
+        // The whale is patient, so waits for the last 20% of the window
+        let whale = BehaviorProfile::whale();
+        assert_eq!(bets(&whale, 1100), 0);
+        assert_eq!(bets(&whale, 1799), 0);
+        assert!(bets(&whale, 1800) > 0);
+        assert_eq!(bets(&whale, 2000), 0, "betting closed");
+
+        // The degen does not
+        assert!(bets(&BehaviorProfile::degen(), 1100) > 0);
+    }
+
+    #[test]
+    fn bets_the_best_round() {
+        let state = funded();
+        let rounds = [
+            DeadPoolRound {
+                round_id: 1,
+                ..round(150, 100)
+            },
+            DeadPoolRound {
+                round_id: 2,
+                ..round(100, 400)
+            },
+        ];
+        let settings = BehaviorSettings::default();
+        let profile = BehaviorProfile::degen();
+
+        let mut placed = 0;
+        for seed in 0..30 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let mut context = PluginContext::new(at(1500), &mut rng, &serde_json::Value::Null);
+            let Some(action) =
+                DeadPoolDecider::decide(&state, &rounds, &profile, &settings, &mut context)
+            else {
+                continue;
+            };
+            assert_eq!(action.data["round_id"], 2);
+            assert_eq!(action.data["is_over"], true);
+            placed += 1;
+        }
+        assert!(placed > 0, "no bet placed");
+
+        let disabled = BehaviorSettings {
+            plays_dead_pool: false,
+            ..BehaviorSettings::default()
+        };
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut context = PluginContext::new(at(1500), &mut rng, &serde_json::Value::Null);
+        assert!(
+            DeadPoolDecider::decide(&state, &rounds, &profile, &disabled, &mut context).is_none()
+        );
+    }
+
+    #[test]
+    fn odds_moving_against_the_bet_abort_it() {
+        let settings = DeadPoolSettings::default();
+        let decided = round(300, 100);
+        let payout = decided.payout_bps(false, data(1));
+
+        // The crowd followed onto UNDER, more than the tolerance allows
+        let crowded = round(300, 200);
+        let reason =
+            DeadPoolDecider::odds_moved(Some(&crowded), false, data(1), payout, &settings).unwrap();
+        assert!(reason.contains("payout fell"), "{reason}");
+
+        // Within the tolerance, or moving for the bet, it goes on
+        let nudged = round(300, 105);
+        assert_eq!(
+            DeadPoolDecider::odds_moved(Some(&nudged), false, data(1), payout, &settings),
+            None
+        );
+        let better = round(400, 100);
+        assert_eq!(
+            DeadPoolDecider::odds_moved(Some(&better), false, data(1), payout, &settings),
+            None
+        );
+
+        // A round that closed is not bet on
+        assert!(DeadPoolDecider::odds_moved(None, false, data(1), payout, &settings).is_some());
+    }
+}
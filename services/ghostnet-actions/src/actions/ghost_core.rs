@@ -5,18 +5,30 @@
 //! - `addStake`: Add to existing position
 //! - `extract`: Exit position and claim rewards
 //! - `claimRewards`: Claim rewards without exiting
+//! - `approve`: Let GhostCore spend DATA, followed up by the stake it is for
+//!
+//! With `reentry_probability` set, a full extraction is followed up by
+//! re-entering at another level.
+//!
+//! With `streak_protection` milestones, positions a scan or two short of one
+//! are held rather than extracted, unless culling risk is elevated or the
+//! profile is impatient.
 
 // Allow suboptimal floating point ops - readability over micro-optimization
 #![allow(clippy::suboptimal_flops)]
 
 use alloy::primitives::U256;
-use fleet_core::plugins::{Action, PluginContext};
+use fleet_core::plugins::{
+    Action, FollowUpAction, FollowUpCondition, PluginContext, checked_action_id,
+};
 use fleet_core::profiles::BehaviorProfile;
 use rand::Rng;
 use tracing::debug;
 
 use crate::config::{BehaviorSettings, LevelSettings};
-use crate::math::{apply_jitter, percentage_of, pct_to_bps};
+use crate::math::{
+    AmountBounds, AmountDistribution, BPS_100_PERCENT, sample_after_tax, sample_stake,
+};
 use crate::state::{GhostnetState, Level};
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -24,16 +36,25 @@ use crate::state::{GhostnetState, Level};
 // ═══════════════════════════════════════════════════════════════════════════════
 
 /// Action ID for jacking into GhostCore.
-pub const ACTION_JACK_IN: &str = "ghostnet.jack_in";
+pub const ACTION_JACK_IN: &str = checked_action_id("ghostnet.jack_in");
 
 /// Action ID for adding stake.
-pub const ACTION_ADD_STAKE: &str = "ghostnet.add_stake";
+pub const ACTION_ADD_STAKE: &str = checked_action_id("ghostnet.add_stake");
 
 /// Action ID for extracting.
-pub const ACTION_EXTRACT: &str = "ghostnet.extract";
+pub const ACTION_EXTRACT: &str = checked_action_id("ghostnet.extract");
 
 /// Action ID for claiming rewards.
-pub const ACTION_CLAIM_REWARDS: &str = "ghostnet.claim_rewards";
+pub const ACTION_CLAIM_REWARDS: &str = checked_action_id("ghostnet.claim_rewards");
+
+/// Action ID for approving GhostCore to spend DATA.
+pub const ACTION_APPROVE: &str = checked_action_id("ghostnet.approve");
+
+/// Delay between an approval and the stake it approves (seconds).
+const APPROVE_STAKE_DELAY_SECS: (u64, u64) = (15, 90);
+
+/// Delay between a full extraction and re-entering (seconds).
+const REENTRY_DELAY_SECS: (u64, u64) = (300, 1800);
 
 // ═══════════════════════════════════════════════════════════════════════════════
 // DECISION LOGIC
@@ -46,15 +67,21 @@ impl GhostCoreDecider {
     /// Decide what GhostCore action to take (if any).
     ///
     /// Decision priority:
-    /// 1. If dead position exists, maybe re-enter
-    /// 2. If alive position exists, maybe extract or compound
-    /// 3. If no position, maybe create one
+    /// 1. If a system reset is near, only get out of an alive position
+    /// 2. If dead position exists, maybe re-enter
+    /// 3. If alive position exists, maybe extract or compound
+    /// 4. If no position, maybe create one
     pub fn decide(
         state: &GhostnetState,
         profile: &BehaviorProfile,
         settings: &BehaviorSettings,
         context: &mut PluginContext<'_>,
     ) -> Option<Action> {
+        let now = u64::try_from(context.now.timestamp()).unwrap_or_default();
+        if state.is_reset_near(now, settings.reset_danger_secs) {
+            return Self::decide_before_reset(state, now);
+        }
+
         // Check if we have a position
         if let Some(ref position) = state.position {
             if position.alive {
@@ -69,6 +96,27 @@ impl GhostCoreDecider {
         Self::decide_new_position(state, profile, settings, context)
     }
 
+    /// Decide what to do while a system reset is near.
+    ///
+    /// Nothing is staked, as the reset would take its penalty from it. An
+    /// alive position is extracted ahead of the reset, or has its rewards
+    /// claimed while it is still locked.
+    fn decide_before_reset(state: &GhostnetState, now: u64) -> Option<Action> {
+        let position = state.active_position()?;
+        let seconds_left = state
+            .reset_timer
+            .and_then(|timer| timer.seconds_left(now));
+        if position.can_extract() {
+            debug!(?seconds_left, "Extracting ahead of system reset");
+            return Some(Action::new(ACTION_EXTRACT, "Extract"));
+        }
+        if position.pending_rewards > U256::ZERO {
+            debug!(?seconds_left, "Claiming rewards ahead of system reset");
+            return Some(Action::new(ACTION_CLAIM_REWARDS, "Claim Rewards"));
+        }
+        None
+    }
+
     /// Decide what to do with an active position.
     fn decide_with_active_position(
         state: &GhostnetState,
@@ -78,22 +126,28 @@ impl GhostCoreDecider {
     ) -> Option<Action> {
         let position = state.position.as_ref()?;
 
-        // First check if we should extract
-        if position.can_extract() && position.ghost_streak >= settings.min_streak_before_extract {
-            // Adjust extract probability based on profile patience
-            // Higher patience = lower extract probability
-            let extract_prob = settings.base_extract_probability * (1.0 - profile.patience * 0.5);
+        // First check if we should extract, unless a streak milestone is close
+        let milestone = Self::milestone_held_for(state, profile, settings);
+        if milestone.is_none() && Self::should_extract(state, profile, settings, context) {
+            let extract = Self::extract(state, profile, settings);
+            return Some(Self::with_reentry(extract, state, profile, settings, context));
+        }
 
-            if context.rng.random_bool(extract_prob) {
-                debug!(
-                    streak = position.ghost_streak,
-                    probability = extract_prob,
-                    "Deciding to extract"
-                );
+        let action = Self::compound_or_claim(state, profile, settings, context)?;
+        Some(match milestone {
+            Some(milestone) => Self::held_for(action, milestone, position.ghost_streak),
+            None => action,
+        })
+    }
 
-                return Some(Action::new(ACTION_EXTRACT, "Extract"));
-            }
-        }
+    /// Decide whether to add to or claim the rewards of the active position.
+    fn compound_or_claim(
+        state: &GhostnetState,
+        profile: &BehaviorProfile,
+        settings: &BehaviorSettings,
+        context: &mut PluginContext<'_>,
+    ) -> Option<Action> {
+        let position = state.position.as_ref()?;
 
         // Check if we should add stake (compound)
         if position.can_add_stake() {
@@ -105,7 +159,8 @@ impl GhostCoreDecider {
                 let compound_prob = settings.base_compound_probability * profile.risk_tolerance;
 
                 if context.rng.random_bool(compound_prob) {
-                    let amount = Self::calculate_add_stake_amount(state, profile, context);
+                    let amount =
+                        Self::calculate_add_stake_amount(state, profile, settings, context);
 
                     if amount > U256::ZERO {
                         debug!(
@@ -140,6 +195,273 @@ impl GhostCoreDecider {
         None
     }
 
+    /// Streak milestone the active position is held for instead of being
+    /// extracted, if it is within the protection window of one.
+    ///
+    /// Positions at elevated culling risk and impatient profiles are not
+    /// held.
+    fn milestone_held_for(
+        state: &GhostnetState,
+        profile: &BehaviorProfile,
+        settings: &BehaviorSettings,
+    ) -> Option<u16> {
+        let protection = &settings.streak_protection;
+        let position = state.active_position()?;
+        if !position.can_extract() || profile.patience < protection.min_patience {
+            return None;
+        }
+        let max_risk_bps = protection.max_cull_risk_bps.min(settings.imminent_cull_risk_bps);
+        if state.culling_risk.is_imminent(max_risk_bps) {
+            return None;
+        }
+        let milestone = protection.milestone_ahead(position.ghost_streak)?;
+        debug!(
+            streak = position.ghost_streak,
+            milestone,
+            risk_bps = state.culling_risk.risk_bps,
+            "Holding position for streak milestone"
+        );
+        Some(milestone)
+    }
+
+    /// `action` noting that the position was held for `milestone` at
+    /// `streak`, which ends up in the action result's detail.
+    fn held_for(mut action: Action, milestone: u16, streak: u16) -> Action {
+        if !action.data.is_object() {
+            action.data = serde_json::json!({});
+        }
+        if let Some(data) = action.data.as_object_mut() {
+            data.insert("rationale".into(), "held for streak milestone".into());
+            data.insert("milestone".into(), milestone.into());
+            data.insert("streak".into(), streak.into());
+        }
+        action
+    }
+
+    /// Decide whether to extract the active position.
+    ///
+    /// Extraction is only considered once the valuation says extracting now
+    /// beats holding for one more scan by the profile's required edge, and
+    /// brings in at least `min_net_extract` after the toll and gas; then it
+    /// happens with the (patience adjusted) extract probability. A position
+    /// about to be culled is extracted as soon as it can be, whatever it
+    /// brings in.
+    fn should_extract(
+        state: &GhostnetState,
+        profile: &BehaviorProfile,
+        settings: &BehaviorSettings,
+        context: &mut PluginContext<'_>,
+    ) -> bool {
+        let Some(position) = state.position.as_ref() else {
+            return false;
+        };
+        if !position.can_extract() {
+            return false;
+        }
+        if state.culling_risk.is_imminent(settings.imminent_cull_risk_bps) {
+            debug!(
+                risk_bps = state.culling_risk.risk_bps,
+                "Extracting ahead of culling"
+            );
+            return true;
+        }
+        if position.ghost_streak < settings.min_streak_before_extract {
+            return false;
+        }
+        let Some(valuation) = state.valuation(&settings.death_rates) else {
+            return false;
+        };
+
+        // Small positions can lose more to the toll and gas than they bring in
+        let net = valuation
+            .exit_value
+            .saturating_sub(U256::from(settings.extract_gas_cost));
+        if net < U256::from(settings.min_net_extract) {
+            debug!(
+                exit_value = %valuation.exit_value,
+                exit_toll = %valuation.exit_toll,
+                net = %net,
+                "Extraction would not pay, holding position"
+            );
+            return false;
+        }
+
+        // Higher patience = more edge required before leaving
+        let required_edge = Self::required_extract_edge_bps(profile, settings);
+        let edge = valuation.extract_edge_bps();
+        if edge <= required_edge {
+            debug!(
+                streak = position.ghost_streak,
+                edge_bps = edge,
+                hold_advantage = %valuation.hold_advantage(),
+                "Holding position"
+            );
+            return false;
+        }
+
+        // Higher patience = lower extract probability
+        let extract_prob = settings.base_extract_probability * (1.0 - profile.patience * 0.5);
+        if !context.rng.random_bool(extract_prob) {
+            return false;
+        }
+
+        debug!(
+            streak = position.ghost_streak,
+            edge_bps = edge,
+            exit_value = %valuation.exit_value,
+            probability = extract_prob,
+            "Deciding to extract"
+        );
+        true
+    }
+
+    /// The extraction of the active position, in part if the extract
+    /// strategy says so.
+    ///
+    /// A partial extraction must leave at least the level's minimum stake.
+    /// Positions about to be culled are extracted in full.
+    fn extract(
+        state: &GhostnetState,
+        profile: &BehaviorProfile,
+        settings: &BehaviorSettings,
+    ) -> Action {
+        let full = Action::new(ACTION_EXTRACT, "Extract");
+        let Some(position) = state.active_position() else {
+            return full;
+        };
+        if state.culling_risk.is_imminent(settings.imminent_cull_risk_bps) {
+            return full;
+        }
+        let Some(amount) = settings.extract_strategy.partial_amount(
+            position.amount,
+            state.extracted,
+            profile.patience,
+        ) else {
+            return full;
+        };
+        let min_stake = LevelSettings::for_level(position.level.as_u8())
+            .map_or(U256::ZERO, |level| U256::from(level.min_stake));
+        if position.amount - amount < min_stake {
+            return full;
+        }
+
+        debug!(
+            amount = %amount,
+            extracted = %state.extracted,
+            strategy = ?settings.extract_strategy,
+            "Deciding to extract in part"
+        );
+        Action::with_data(
+            ACTION_EXTRACT,
+            "Extract",
+            serde_json::json!({
+                "amount": amount.to_string(),
+            }),
+        )
+    }
+
+    /// Edge of extracting over holding the profile wants before extracting
+    /// (basis points of the exit value).
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)] // Clamped to 0 - 10000
+    fn required_extract_edge_bps(profile: &BehaviorProfile, settings: &BehaviorSettings) -> u64 {
+        let patience_bps = (profile.patience.clamp(0.0, 1.0) * 10_000.0).round() as u64;
+        settings.min_extract_edge_bps.saturating_mul(patience_bps) / BPS_100_PERCENT
+    }
+
+    /// Follow a full extraction up with re-entering at another level, as
+    /// often as `reentry_probability` says.
+    ///
+    /// The re-entry is sized from the balance after the extraction, counting
+    /// the position's stake as brought in.
+    fn with_reentry(
+        extract: Action,
+        state: &GhostnetState,
+        profile: &BehaviorProfile,
+        settings: &BehaviorSettings,
+        context: &mut PluginContext<'_>,
+    ) -> Action {
+        // Partial extractions keep the position
+        if settings.reentry_probability <= 0.0 || !extract.data.is_null() {
+            return extract;
+        }
+        let Some(position) = state.active_position() else {
+            return extract;
+        };
+        if !context.rng.random_bool(settings.reentry_probability.min(1.0)) {
+            return extract;
+        }
+
+        let level = Self::select_level_except(position.level, profile, context);
+        let mut after = state.clone();
+        after.data_balance = state.data_balance.saturating_add(position.amount);
+        after.position = None;
+        let amount = Self::calculate_entry_amount(&after, profile, level, settings, context);
+        if amount.is_zero() {
+            return extract;
+        }
+
+        debug!(level = %level, amount = %amount, "Deciding to re-enter after extracting");
+        let (min, max) = REENTRY_DELAY_SECS;
+        let reentry = Self::approved(Self::jack_in(amount, level), &after, settings);
+        extract.with_follow_up(
+            FollowUpAction::of(reentry, FollowUpCondition::OnSuccess).with_delay_secs(min, max),
+        )
+    }
+
+    /// The stake `action` preceded by approving GhostCore for its amount, if
+    /// `approve_before_stake` is on and the allowance does not cover it.
+    ///
+    /// Other actions are returned as they are.
+    pub(crate) fn approved(
+        action: Action,
+        state: &GhostnetState,
+        settings: &BehaviorSettings,
+    ) -> Action {
+        if !settings.approve_before_stake
+            || !matches!(action.id.as_str(), ACTION_JACK_IN | ACTION_ADD_STAKE)
+        {
+            return action;
+        }
+        let Some(amount) = action.data["amount"]
+            .as_str()
+            .and_then(|amount| amount.parse::<U256>().ok())
+        else {
+            return action;
+        };
+        if state.ghost_core_allowance >= amount {
+            return action;
+        }
+
+        debug!(
+            amount = %amount,
+            allowance = %state.ghost_core_allowance,
+            "Approving GhostCore before staking"
+        );
+        let (min, max) = APPROVE_STAKE_DELAY_SECS;
+        Action::with_data(
+            ACTION_APPROVE,
+            "Approve",
+            serde_json::json!({
+                "amount": amount.to_string(),
+            }),
+        )
+        .with_follow_up(
+            FollowUpAction::of(action, FollowUpCondition::OnSuccess).with_delay_secs(min, max),
+        )
+    }
+
+    /// Jacking in with `amount` at `level`.
+    fn jack_in(amount: U256, level: Level) -> Action {
+        Action::with_data(
+            ACTION_JACK_IN,
+            "Jack In",
+            serde_json::json!({
+                "amount": amount.to_string(),
+                "level": level.as_u8(),
+            }),
+        )
+    }
+
     /// Decide what to do after position died.
     fn decide_after_death(
         state: &GhostnetState,
@@ -161,7 +483,7 @@ impl GhostCoreDecider {
 
         if context.rng.random_bool(reentry_prob) {
             let level = Self::select_level(profile, context);
-            let amount = Self::calculate_entry_amount(state, profile, level, context);
+            let amount = Self::calculate_entry_amount(state, profile, level, settings, context);
 
             if amount > U256::ZERO {
                 debug!(
@@ -171,14 +493,7 @@ impl GhostCoreDecider {
                     "Deciding to re-enter after death"
                 );
 
-                return Some(Action::with_data(
-                    ACTION_JACK_IN,
-                    "Jack In",
-                    serde_json::json!({
-                        "amount": amount.to_string(),
-                        "level": level.as_u8(),
-                    }),
-                ));
+                return Some(Self::jack_in(amount, level));
             }
         }
 
@@ -202,7 +517,7 @@ impl GhostCoreDecider {
 
         if context.rng.random_bool(entry_prob.min(0.9)) {
             let level = Self::select_level(profile, context);
-            let amount = Self::calculate_entry_amount(state, profile, level, context);
+            let amount = Self::calculate_entry_amount(state, profile, level, settings, context);
 
             if amount > U256::ZERO {
                 debug!(
@@ -212,14 +527,7 @@ impl GhostCoreDecider {
                     "Deciding to create new position"
                 );
 
-                return Some(Action::with_data(
-                    ACTION_JACK_IN,
-                    "Jack In",
-                    serde_json::json!({
-                        "amount": amount.to_string(),
-                        "level": level.as_u8(),
-                    }),
-                ));
+                return Some(Self::jack_in(amount, level));
             }
         }
 
@@ -228,6 +536,25 @@ impl GhostCoreDecider {
 
     /// Select a level based on profile risk tolerance.
     fn select_level(profile: &BehaviorProfile, context: &mut PluginContext<'_>) -> Level {
+        Self::pick_level(&Self::eligible_levels(profile), context)
+    }
+
+    /// Select a level other than `current`, unless the profile's risk
+    /// tolerance allows no other.
+    fn select_level_except(
+        current: Level,
+        profile: &BehaviorProfile,
+        context: &mut PluginContext<'_>,
+    ) -> Level {
+        let mut eligible = Self::eligible_levels(profile);
+        if eligible.len() > 1 {
+            eligible.retain(|level| *level != current);
+        }
+        Self::pick_level(&eligible, context)
+    }
+
+    /// Levels the profile's risk tolerance allows.
+    fn eligible_levels(profile: &BehaviorProfile) -> Vec<Level> {
         // Build weighted distribution based on risk tolerance
         let levels = [
             (Level::Vault, 0.2),
@@ -238,15 +565,18 @@ impl GhostCoreDecider {
         ];
 
         // Filter to levels the profile's risk tolerance allows
-        let eligible: Vec<_> = levels
+        levels
             .iter()
             .filter(|(level, min_risk)| {
                 profile.risk_tolerance >= *min_risk
                     && LevelSettings::for_level(level.as_u8()).is_some()
             })
             .map(|(level, _)| *level)
-            .collect();
+            .collect()
+    }
 
+    /// Pick one of the `eligible` levels, the Vault if there are none.
+    fn pick_level(eligible: &[Level], context: &mut PluginContext<'_>) -> Level {
         if eligible.is_empty() {
             return Level::Vault;
         }
@@ -257,59 +587,49 @@ impl GhostCoreDecider {
     }
 
     /// Calculate entry amount based on balance and profile.
-    fn calculate_entry_amount(
+    ///
+    /// Sampled within the level's stake limits, keeping the configured
+    /// reserve, see [`sample_stake`].
+    pub(crate) fn calculate_entry_amount(
         state: &GhostnetState,
         profile: &BehaviorProfile,
         level: Level,
+        settings: &BehaviorSettings,
         context: &mut PluginContext<'_>,
     ) -> U256 {
-        let Some(settings) = LevelSettings::for_level(level.as_u8()) else {
+        let Some(level_settings) = LevelSettings::for_level(level.as_u8()) else {
             return U256::ZERO;
         };
 
-        let min_stake = U256::from(settings.min_stake);
-        if state.data_balance < min_stake {
-            return U256::ZERO;
-        }
-
-        // Calculate percentage of balance to stake using basis points (safe integer math)
-        // Higher risk tolerance = higher percentage
-        // Risk 0.0 -> 10% (1000 bps), Risk 1.0 -> 50% (5000 bps)
-        let base_bps = pct_to_bps(0.1 + (profile.risk_tolerance * 0.4));
-
-        // Add some randomness (80% to 120% of base, capped at 80%)
-        let jittered_bps = apply_jitter(base_bps, 0.8, 1.2, context.rng).min(8000);
-
-        // Calculate amount using integer arithmetic
-        let amount = percentage_of(state.data_balance, jittered_bps);
-
-        // Clamp to min/max
-        amount.max(min_stake).min(state.data_balance)
+        let bounds = AmountBounds::new(
+            U256::from(level_settings.min_stake),
+            U256::from(level_settings.max_stake),
+        )
+        .with_reserve(U256::from(settings.data_reserve));
+        let tax_bps = u64::from(state.transfer_tax.ghost_core_bps);
+        sample_after_tax(state.data_balance, &bounds, tax_bps, |balance, bounds| {
+            sample_stake(profile, balance, bounds, context.rng)
+        })
     }
 
     /// Calculate add stake amount.
+    ///
+    /// Top-ups are sampled like entries at half their median size.
     fn calculate_add_stake_amount(
         state: &GhostnetState,
         profile: &BehaviorProfile,
+        settings: &BehaviorSettings,
         context: &mut PluginContext<'_>,
     ) -> U256 {
-        // Add 10-30% of current balance, adjusted by risk tolerance
-        // Risk 0.0 -> 10% (1000 bps), Risk 1.0 -> 30% (3000 bps)
-        let base_bps = pct_to_bps(0.1 + (profile.risk_tolerance * 0.2));
-
-        // Add jitter (80% to 120% of base, capped at 50%)
-        let jittered_bps = apply_jitter(base_bps, 0.8, 1.2, context.rng).min(5000);
-
-        // Calculate amount using integer arithmetic
-        let amount = percentage_of(state.data_balance, jittered_bps);
-
         // Don't add less than 1 DATA
         let min = U256::from(1_000_000_000_000_000_000_u128);
-        if amount < min {
-            return U256::ZERO;
-        }
-
-        amount.min(state.data_balance)
+        let bounds =
+            AmountBounds::new(min, U256::MAX).with_reserve(U256::from(settings.data_reserve));
+        let distribution = AmountDistribution::stake(profile).scaled(0.5);
+        let tax_bps = u64::from(state.transfer_tax.ghost_core_bps);
+        sample_after_tax(state.data_balance, &bounds, tax_bps, |balance, bounds| {
+            distribution.sample(balance, bounds, context.rng)
+        })
     }
 }
 
@@ -318,16 +638,63 @@ impl GhostCoreDecider {
 // ═══════════════════════════════════════════════════════════════════════════════
 
 #[cfg(test)]
+#[allow(clippy::unwrap_used)]
 mod tests {
     use super::*;
+    use crate::config::{ExtractStrategy, StreakProtection};
+    use crate::math::DeathRateTable;
+    use crate::math::gross_for_net;
+    use crate::state::{CullingRisk, ExitToll, Position, ResetTimer, TransferTax};
     use chrono::Utc;
     use rand::rngs::StdRng;
     use rand::SeedableRng;
 
-    fn test_context<'a>(rng: &'a mut StdRng) -> PluginContext<'a> {
+    const DATA: u128 = 1_000_000_000_000_000_000;
+
+    fn test_context(rng: &mut StdRng) -> PluginContext<'_> {
         PluginContext::new(Utc::now(), rng, &serde_json::Value::Null)
     }
 
+    /// A live position past the extraction streak, with no DATA to compound.
+    fn state_with(level: Level, stake: u128, rewards: u128, death_rate_bps: u16) -> GhostnetState {
+        GhostnetState {
+            position: Some(Position {
+                amount: U256::from(stake * DATA),
+                level,
+                entry_timestamp: 0,
+                last_add_timestamp: 0,
+                alive: true,
+                ghost_streak: 3,
+                pending_rewards: U256::from(rewards * DATA),
+                effective_death_rate_bps: death_rate_bps,
+                in_lock_period: false,
+                active_boosts: Vec::new(),
+            }),
+            ..GhostnetState::default()
+        }
+    }
+
+    /// Count the extractions over a number of decisions.
+    fn extractions(
+        state: &GhostnetState,
+        profile: &BehaviorProfile,
+        settings: &BehaviorSettings,
+    ) -> usize {
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut context = test_context(&mut rng);
+        (0..50)
+            .filter_map(|_| GhostCoreDecider::decide(state, profile, settings, &mut context))
+            .filter(|action| action.id.0 == ACTION_EXTRACT)
+            .count()
+    }
+
+    fn always_extracting() -> BehaviorSettings {
+        BehaviorSettings {
+            base_extract_probability: 1.0,
+            ..BehaviorSettings::default()
+        }
+    }
+
     #[test]
     fn no_action_when_insufficient_balance() {
         let state = GhostnetState::default();
@@ -367,4 +734,448 @@ mod tests {
             "degen avg {avg_high} should be > whale avg {avg_low}"
         );
     }
+
+    #[test]
+    fn stakes_land_in_full_after_the_transfer_tax() {
+        // What GhostCore would receive of a transfer under a 10% tax, worked
+        // out as DataToken does
+        let landed = |sent: U256| sent - sent * U256::from(1000) / U256::from(10_000);
+        let settings = BehaviorSettings {
+            data_reserve: 5 * DATA,
+            ..BehaviorSettings::default()
+        };
+        let reserve = U256::from(settings.data_reserve);
+        let profile = BehaviorProfile::degen();
+
+        // 1000 DATA to stake after the reserve, sent as is or taxed on the way
+        let untaxed = GhostnetState {
+            data_balance: U256::from(1000 * DATA) + reserve,
+            ..GhostnetState::default()
+        };
+        let taxed = GhostnetState {
+            data_balance: gross_for_net(U256::from(1000 * DATA), 1000).unwrap() + reserve,
+            transfer_tax: TransferTax {
+                ghost_core_bps: 1000,
+                arcade_core_bps: 0,
+            },
+            ..GhostnetState::default()
+        };
+
+        for seed in 0..50 {
+            let entry = |state: &GhostnetState| {
+                let mut rng = StdRng::seed_from_u64(seed);
+                let mut context = test_context(&mut rng);
+                GhostCoreDecider::calculate_entry_amount(
+                    state,
+                    &profile,
+                    Level::Darknet,
+                    &settings,
+                    &mut context,
+                )
+            };
+            let top_up = |state: &GhostnetState| {
+                let mut rng = StdRng::seed_from_u64(seed);
+                let mut context = test_context(&mut rng);
+                GhostCoreDecider::calculate_add_stake_amount(
+                    state,
+                    &profile,
+                    &settings,
+                    &mut context,
+                )
+            };
+
+            let sizes: [&dyn Fn(&GhostnetState) -> U256; 2] = [&entry, &top_up];
+            for size in sizes {
+                let intended = size(&untaxed);
+                let sent = size(&taxed);
+                assert!(!intended.is_zero());
+                assert_eq!(landed(sent), intended, "seed {seed}");
+                assert!(sent <= taxed.data_balance - reserve, "seed {seed}");
+            }
+        }
+    }
+
+    #[test]
+    fn extracts_risky_positions() {
+        // SUBNET: holding 1000 + 10 at 75% is worth far less than leaving
+        let state = state_with(Level::Subnet, 1000, 30, 2500);
+        let settings = always_extracting();
+        assert!(extractions(&state, &BehaviorProfile::grinder(), &settings) > 0);
+
+        // Unless the position is still locked, or young
+        let mut locked = state.clone();
+        if let Some(position) = locked.position.as_mut() {
+            position.in_lock_period = true;
+        }
+        assert_eq!(extractions(&locked, &BehaviorProfile::grinder(), &settings), 0);
+
+        let mut young = state;
+        if let Some(position) = young.position.as_mut() {
+            position.ghost_streak = 1;
+        }
+        assert_eq!(extractions(&young, &BehaviorProfile::grinder(), &settings), 0);
+    }
+
+    #[test]
+    fn holds_positions_worth_more_than_their_exit() {
+        // VAULT at 2.5%: 130 (+10 over the scan) at 97.5% beats leaving with 130
+        let state = state_with(Level::Vault, 100, 30, 250);
+        let valuation = state.valuation(&DeathRateTable::new()).unwrap();
+        assert!(!valuation.favors_extract());
+
+        let settings = always_extracting();
+        assert_eq!(extractions(&state, &BehaviorProfile::degen(), &settings), 0);
+    }
+
+    #[test]
+    fn holds_positions_not_worth_extracting() {
+        // SUBNET: 2 DATA at 25% are better off extracted...
+        let state = state_with(Level::Subnet, 2, 0, 2500);
+        let settings = always_extracting();
+        assert!(extractions(&state, &BehaviorProfile::grinder(), &settings) > 0);
+
+        // ...unless toll and gas leave less than the minimum: 2 - 0.5 - 0.6
+        let mut tolled = state;
+        tolled.exit_toll = ExitToll { penalty_bps: 2500 };
+        let settings = BehaviorSettings {
+            extract_gas_cost: 600_000_000_000_000_000,
+            ..settings
+        };
+        assert_eq!(extractions(&tolled, &BehaviorProfile::grinder(), &settings), 0);
+
+        let settings = BehaviorSettings {
+            min_net_extract: 900_000_000_000_000_000,
+            ..settings
+        };
+        assert!(extractions(&tolled, &BehaviorProfile::grinder(), &settings) > 0);
+    }
+
+    #[test]
+    fn extracts_ahead_of_culling() {
+        // Worth holding, too young and too small to extract
+        let mut state = state_with(Level::Vault, 1, 30, 250);
+        if let Some(position) = state.position.as_mut() {
+            position.ghost_streak = 0;
+        }
+        let settings = BehaviorSettings {
+            // Never extracts of its own accord
+            base_extract_probability: 0.0,
+            min_net_extract: u128::MAX,
+            ..BehaviorSettings::default()
+        };
+        let profile = BehaviorProfile::whale();
+        assert_eq!(extractions(&state, &profile, &settings), 0);
+
+        // Eligible for culling, but not likely enough
+        state.culling_risk = CullingRisk {
+            risk_bps: 4000,
+            eligible: true,
+            capacity_bps: 10_000,
+        };
+        assert_eq!(extractions(&state, &profile, &settings), 0);
+
+        state.culling_risk.risk_bps = 6000;
+        assert_eq!(extractions(&state, &profile, &settings), 50);
+
+        // Locked positions still cannot leave
+        if let Some(position) = state.position.as_mut() {
+            position.in_lock_period = true;
+        }
+        assert_eq!(extractions(&state, &profile, &settings), 0);
+    }
+
+    #[test]
+    fn patient_profiles_want_a_larger_edge() {
+        // VAULT without rewards: extracting has an edge of 5%
+        let state = state_with(Level::Vault, 1000, 0, 500);
+        let settings = BehaviorSettings {
+            min_extract_edge_bps: 1000,
+            ..always_extracting()
+        };
+
+        // Whales (patience 0.9) want 9%, degens (0.3) 3%
+        assert_eq!(
+            GhostCoreDecider::required_extract_edge_bps(&BehaviorProfile::whale(), &settings),
+            900
+        );
+        assert_eq!(
+            GhostCoreDecider::required_extract_edge_bps(&BehaviorProfile::degen(), &settings),
+            300
+        );
+        assert_eq!(extractions(&state, &BehaviorProfile::whale(), &settings), 0);
+        assert!(extractions(&state, &BehaviorProfile::degen(), &settings) > 0);
+    }
+
+    /// A reset timer running out `seconds_left` from now.
+    fn reset_in(seconds_left: i64) -> ResetTimer {
+        let deadline = Utc::now().timestamp() + seconds_left;
+        ResetTimer {
+            deadline: u64::try_from(deadline).unwrap(),
+            epoch: 1,
+            penalty_bps: 0,
+        }
+    }
+
+    /// The actions taken over a number of decisions.
+    fn decisions(state: &GhostnetState, settings: &BehaviorSettings) -> Vec<String> {
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut context = test_context(&mut rng);
+        let profile = BehaviorProfile::degen();
+        (0..50)
+            .filter_map(|_| GhostCoreDecider::decide(state, &profile, settings, &mut context))
+            .map(|action| action.id.0)
+            .collect()
+    }
+
+    #[test]
+    fn extracts_in_part_by_strategy() {
+        let extract = |state: &GhostnetState, settings: &BehaviorSettings| {
+            let mut rng = StdRng::seed_from_u64(7);
+            let mut context = test_context(&mut rng);
+            let profile = BehaviorProfile::grinder();
+            let action =
+                GhostCoreDecider::decide(state, &profile, settings, &mut context).unwrap();
+            assert_eq!(action.id.0, ACTION_EXTRACT);
+            action.data.get("amount").and_then(|amount| amount.as_str()?.parse::<U256>().ok())
+        };
+        let state = state_with(Level::Subnet, 1000, 30, 2500);
+        let settings = BehaviorSettings {
+            extract_strategy: ExtractStrategy::TakeProfits { keep_bps: 4000 },
+            ..always_extracting()
+        };
+        assert_eq!(extract(&state, &always_extracting()), None);
+        let amount = extract(&state, &settings).unwrap();
+        assert!(amount > U256::from(300 * DATA) && amount <= U256::from(600 * DATA));
+
+        // The base goes once profits were taken
+        let mut taken = state.clone();
+        taken.extracted = U256::from(600 * DATA);
+        assert_eq!(extract(&taken, &settings), None);
+
+        // Positions about to be culled leave in full
+        let mut culled = state;
+        culled.culling_risk = CullingRisk {
+            risk_bps: 9000,
+            eligible: true,
+            capacity_bps: 10_000,
+        };
+        assert_eq!(extract(&culled, &settings), None);
+
+        // So do those that would keep less than SUBNET's 50 DATA minimum
+        let small = state_with(Level::Subnet, 60, 30, 2500);
+        assert_eq!(extract(&small, &settings), None);
+    }
+
+    #[test]
+    fn stops_staking_when_reset_is_near() {
+        let settings = BehaviorSettings {
+            base_compound_probability: 1.0,
+            ..BehaviorSettings::default()
+        };
+        let danger = i64::try_from(settings.reset_danger_secs).unwrap();
+        let funded = GhostnetState {
+            data_balance: U256::from(1000 * DATA),
+            ..GhostnetState::default()
+        };
+
+        // Far from the reset, or without a timer, wallets enter
+        for timer in [None, Some(reset_in(danger * 4)), Some(reset_in(danger + 60))] {
+            let state = GhostnetState {
+                reset_timer: timer,
+                ..funded.clone()
+            };
+            let actions = decisions(&state, &settings);
+            assert!(actions.iter().any(|id| id == ACTION_JACK_IN), "{timer:?}");
+        }
+
+        // Close to it, or past it, they do not
+        for timer in [Some(reset_in(danger)), Some(reset_in(60)), Some(reset_in(-60))] {
+            let state = GhostnetState {
+                reset_timer: timer,
+                ..funded.clone()
+            };
+            assert!(decisions(&state, &settings).is_empty(), "{timer:?}");
+        }
+
+        // Unless reset awareness is disabled
+        let state = GhostnetState {
+            reset_timer: Some(reset_in(0)),
+            ..funded
+        };
+        let disabled = BehaviorSettings {
+            reset_danger_secs: 0,
+            ..settings
+        };
+        assert!(!decisions(&state, &disabled).is_empty());
+    }
+
+    #[test]
+    fn leaves_positions_ahead_of_reset() {
+        // Worth holding and compounding, with DATA to compound
+        let mut state = state_with(Level::Vault, 100, 30, 250);
+        state.data_balance = U256::from(1000 * DATA);
+        let settings = BehaviorSettings {
+            base_compound_probability: 1.0,
+            ..BehaviorSettings::default()
+        };
+        let danger = i64::try_from(settings.reset_danger_secs).unwrap();
+        state.reset_timer = Some(reset_in(danger * 2));
+        let actions = decisions(&state, &settings);
+        assert!(actions.iter().any(|id| id == ACTION_ADD_STAKE));
+        assert!(!actions.iter().any(|id| id == ACTION_EXTRACT));
+
+        // Near the reset, every decision extracts
+        state.reset_timer = Some(reset_in(danger / 2));
+        let actions = decisions(&state, &settings);
+        assert_eq!(actions.len(), 50);
+        assert!(actions.iter().all(|id| id == ACTION_EXTRACT));
+
+        // Locked positions claim their rewards instead, if they have any
+        if let Some(position) = state.position.as_mut() {
+            position.in_lock_period = true;
+        }
+        let actions = decisions(&state, &settings);
+        assert!(actions.iter().all(|id| id == ACTION_CLAIM_REWARDS));
+        if let Some(position) = state.position.as_mut() {
+            position.pending_rewards = U256::ZERO;
+        }
+        assert!(decisions(&state, &settings).is_empty());
+    }
+
+    #[test]
+    fn stakes_are_approved_when_the_allowance_is_short() {
+        let mut state = GhostnetState::default();
+        let jack_in = GhostCoreDecider::jack_in(U256::from(100 * DATA), Level::Darknet);
+        let settings = BehaviorSettings {
+            approve_before_stake: true,
+            ..BehaviorSettings::default()
+        };
+
+        let approve = GhostCoreDecider::approved(jack_in.clone(), &state, &settings);
+        assert_eq!(approve.id.as_str(), ACTION_APPROVE);
+        assert_eq!(approve.data["amount"], jack_in.data["amount"]);
+        assert_eq!(approve.follow_up[0].to_action(1).data, jack_in.data);
+        assert_eq!(approve.follow_up[0].condition, FollowUpCondition::OnSuccess);
+
+        // Covered allowances and other actions need no approval
+        state.ghost_core_allowance = U256::from(100 * DATA);
+        let approved = |action: &Action, state: &GhostnetState, settings: &BehaviorSettings| {
+            GhostCoreDecider::approved(action.clone(), state, settings).id
+        };
+        assert_eq!(approved(&jack_in, &state, &settings), jack_in.id);
+        let extract = Action::new(ACTION_EXTRACT, "Extract");
+        assert_eq!(approved(&extract, &state, &settings), extract.id);
+
+        state.ghost_core_allowance = U256::ZERO;
+        assert_eq!(approved(&jack_in, &state, &BehaviorSettings::default()), jack_in.id);
+    }
+
+    #[test]
+    fn full_extractions_re_enter_at_another_level() {
+        let state = state_with(Level::Subnet, 1000, 30, 2500);
+        let profile = BehaviorProfile::grinder();
+        let settings = BehaviorSettings {
+            reentry_probability: 1.0,
+            approve_before_stake: true,
+            ..always_extracting()
+        };
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut context = test_context(&mut rng);
+
+        let decided = (0..50)
+            .filter_map(|_| GhostCoreDecider::decide(&state, &profile, &settings, &mut context))
+            .filter(|action| action.id.0 == ACTION_EXTRACT && action.data.is_null())
+            .collect::<Vec<_>>();
+        assert!(!decided.is_empty());
+        for extract in decided {
+            // Extract, approve the stake it brought in, then jack in again
+            let approve = extract.follow_up[0].to_action(1);
+            assert_eq!(approve.id.as_str(), ACTION_APPROVE);
+            let jack_in = approve.follow_up[0].to_action(2);
+            assert_eq!(jack_in.id.as_str(), ACTION_JACK_IN);
+            assert_ne!(jack_in.data["level"], Level::Subnet.as_u8());
+        }
+
+        // Re-entry is off by default
+        let settings = always_extracting();
+        let action = GhostCoreDecider::decide(&state, &profile, &settings, &mut context);
+        assert!(action.is_some_and(|action| action.follow_up.is_empty()));
+    }
+
+    /// A position extracted every so often, held for streaks 10 and 20.
+    fn protected_at_streak(streak: u16) -> (GhostnetState, BehaviorSettings) {
+        let mut state = state_with(Level::Subnet, 1000, 30, 2500);
+        if let Some(position) = state.position.as_mut() {
+            position.ghost_streak = streak;
+        }
+        let settings = BehaviorSettings {
+            streak_protection: StreakProtection {
+                milestones: vec![10, 20],
+                ..StreakProtection::new()
+            },
+            ..always_extracting()
+        };
+        (state, settings)
+    }
+
+    #[test]
+    fn holds_positions_within_the_window_of_a_streak_milestone() {
+        let profile = BehaviorProfile::grinder();
+        for streak in [8, 9, 18, 19] {
+            let (state, settings) = protected_at_streak(streak);
+            assert_eq!(extractions(&state, &profile, &settings), 0, "streak {streak}");
+        }
+        for streak in [7, 10, 11] {
+            let (state, settings) = protected_at_streak(streak);
+            assert!(extractions(&state, &profile, &settings) > 0, "streak {streak}");
+        }
+
+        // Without milestones nothing is held
+        let (state, _) = protected_at_streak(9);
+        assert!(extractions(&state, &profile, &always_extracting()) > 0);
+    }
+
+    #[test]
+    fn elevated_culling_risk_overrides_streak_protection() {
+        let profile = BehaviorProfile::grinder();
+        let (mut state, settings) = protected_at_streak(9);
+        state.culling_risk = CullingRisk {
+            risk_bps: settings.streak_protection.max_cull_risk_bps - 1,
+            eligible: true,
+            capacity_bps: 10_000,
+        };
+        assert_eq!(extractions(&state, &profile, &settings), 0);
+
+        state.culling_risk.risk_bps = settings.streak_protection.max_cull_risk_bps;
+        assert!(extractions(&state, &profile, &settings) > 0);
+    }
+
+    #[test]
+    fn impatient_profiles_skip_streak_protection() {
+        let (state, settings) = protected_at_streak(9);
+        let impatient = BehaviorProfile {
+            patience: settings.streak_protection.min_patience / 2.0,
+            ..BehaviorProfile::grinder()
+        };
+        assert!(extractions(&state, &impatient, &settings) > 0);
+    }
+
+    #[test]
+    fn held_positions_note_the_milestone() {
+        let (state, settings) = protected_at_streak(9);
+        let profile = BehaviorProfile::grinder();
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut context = test_context(&mut rng);
+
+        let claims = (0..50)
+            .filter_map(|_| GhostCoreDecider::decide(&state, &profile, &settings, &mut context))
+            .collect::<Vec<_>>();
+        assert!(!claims.is_empty());
+        for claim in claims {
+            assert_eq!(claim.id.as_str(), ACTION_CLAIM_REWARDS);
+            assert_eq!(claim.data["rationale"], "held for streak milestone");
+            assert_eq!(claim.data["milestone"], 10);
+            assert_eq!(claim.data["streak"], 9);
+        }
+    }
 }
@@ -2,6 +2,16 @@
 //!
 //! This module handles decisions for:
 //! - `hashcrash_bet`: Place a bet in the current round
+//!
+//! # Cashing Out
+//!
+//! HashCrash is a pre-commit game: the target multiplier passed to
+//! `placeBet` *is* the cash-out point, and the crash point is only revealed
+//! after betting closes. The contract has no separate cashout call and no
+//! live multiplier to watch, so there is no `hashcrash_cashout` action; the
+//! cash-out strategy lives entirely in the target multiplier chosen here
+//! (derived from risk tolerance, with jitter so wallets don't all pick the
+//! same target).
 
 // Allow precision loss for target multiplier calculations (small integers, not tokens)
 #![allow(clippy::cast_precision_loss)]
@@ -9,13 +19,13 @@
 #![allow(clippy::suboptimal_flops)]
 
 use alloy::primitives::U256;
-use fleet_core::plugins::{Action, PluginContext};
+use fleet_core::plugins::{Action, PluginContext, checked_action_id};
 use fleet_core::profiles::BehaviorProfile;
 use rand::Rng;
 use tracing::debug;
 
 use crate::config::BehaviorSettings;
-use crate::math::{percentage_of, pct_to_bps, random_bps};
+use crate::math::{AmountBounds, percentage_of, sample_after_tax, sample_bet};
 use crate::state::GhostnetState;
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -23,7 +33,7 @@ use crate::state::GhostnetState;
 // ═══════════════════════════════════════════════════════════════════════════════
 
 /// Action ID for placing a HashCrash bet.
-pub const ACTION_HASHCRASH_BET: &str = "ghostnet.hashcrash_bet";
+pub const ACTION_HASHCRASH_BET: &str = checked_action_id("ghostnet.hashcrash_bet");
 
 // ═══════════════════════════════════════════════════════════════════════════════
 // CONSTANTS
@@ -36,7 +46,7 @@ const MIN_TARGET: u16 = 101;
 const MAX_TARGET: u16 = 10000;
 
 /// Minimum bet amount (1 DATA).
-const MIN_BET: u128 = 1_000_000_000_000_000_000;
+pub const MIN_BET: u128 = 1_000_000_000_000_000_000;
 
 // ═══════════════════════════════════════════════════════════════════════════════
 // DECISION LOGIC
@@ -106,6 +116,7 @@ impl HashCrashDecider {
             ACTION_HASHCRASH_BET,
             "HashCrash Bet",
             serde_json::json!({
+                "round_id": round.round_id,
                 "amount": amount.to_string(),
                 "target_multiplier": target,
             }),
@@ -114,29 +125,32 @@ impl HashCrashDecider {
 
     /// Calculate bet amount based on balance and settings.
     ///
-    /// Uses basis-point arithmetic for precision with large token amounts.
+    /// Sampled between the minimum bet and 10% of the balance, see
+    /// [`sample_bet`]. After `hashcrash_loss_streak` losses in a row the
+    /// median bet is cut to `hashcrash_loss_streak_bet_pct` of its usual size.
     fn calculate_bet_amount(
         state: &GhostnetState,
         profile: &BehaviorProfile,
         settings: &BehaviorSettings,
         context: &mut PluginContext<'_>,
     ) -> U256 {
-        // Calculate max bet percentage using basis points
-        let max_bps = pct_to_bps(settings.max_hashcrash_bet_pct * profile.risk_tolerance);
+        let mut max_share = settings.max_hashcrash_bet_pct;
 
-        // Apply jitter: 30% to 100% of max
-        let jitter_bps = random_bps(0.3, 1.0, context.rng);
-        // Safe: max_bps * jitter_bps / 10000 fits in u64 (max ~10000 * 10000 / 10000 = 10000)
-        #[allow(clippy::cast_possible_truncation)]
-        let bet_bps = (u128::from(max_bps) * u128::from(jitter_bps) / 10_000) as u64;
-
-        // Calculate amount using integer arithmetic
-        let amount = percentage_of(state.data_balance, bet_bps);
+        // Bet smaller while on a losing streak
+        let losses = state.pnl.consecutive_losses();
+        if settings.hashcrash_loss_streak > 0 && losses >= settings.hashcrash_loss_streak {
+            debug!(losses, "Reducing HashCrash bet after losing streak");
+            max_share *= settings.hashcrash_loss_streak_bet_pct;
+        }
 
         // Ensure within bounds: min bet to 10% of balance
-        let min = U256::from(MIN_BET);
         let max = percentage_of(state.data_balance, 1000); // 10% = 1000 bps
-        amount.max(min).min(max)
+        let bounds = AmountBounds::new(U256::from(MIN_BET), max)
+            .with_reserve(U256::from(settings.data_reserve));
+        let tax_bps = u64::from(state.transfer_tax.arcade_core_bps);
+        sample_after_tax(state.data_balance, &bounds, tax_bps, |balance, bounds| {
+            sample_bet(profile, balance, max_share, bounds, context.rng)
+        })
     }
 
     /// Calculate target multiplier based on risk tolerance.
@@ -169,11 +183,12 @@ impl HashCrashDecider {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::state::BetOutcome;
     use chrono::Utc;
     use rand::rngs::StdRng;
     use rand::SeedableRng;
 
-    fn test_context<'a>(rng: &'a mut StdRng) -> PluginContext<'a> {
+    fn test_context(rng: &mut StdRng) -> PluginContext<'_> {
         PluginContext::new(Utc::now(), rng, &serde_json::Value::Null)
     }
 
@@ -202,6 +217,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::field_reassign_with_default)]
     fn no_bet_when_no_round() {
         let mut state = GhostnetState::default();
         state.data_balance = U256::from(100_000_000_000_000_000_000_u128);
@@ -263,4 +279,39 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn losing_streak_reduces_bet() {
+        let mut state = GhostnetState {
+            data_balance: U256::from(1_000_000_000_000_000_000_000_u128), // 1000 DATA
+            ..GhostnetState::default()
+        };
+        let profile = BehaviorProfile::degen();
+        let settings = BehaviorSettings::default();
+        let amount = |state: &GhostnetState| {
+            let mut rng = StdRng::seed_from_u64(7);
+            let mut context = test_context(&mut rng);
+            HashCrashDecider::calculate_bet_amount(state, &profile, &settings, &mut context)
+        };
+        let usual = amount(&state);
+
+        // One loss short of the streak
+        for round in 1..settings.hashcrash_loss_streak {
+            state.pnl.record_bet(u64::from(round), U256::from(1), 200, 0);
+            state.pnl.resolve(u64::from(round), BetOutcome::Lost, U256::ZERO);
+        }
+        assert_eq!(amount(&state), usual);
+
+        state.pnl.record_bet(99, U256::from(1), 200, 0);
+        state.pnl.resolve(99, BetOutcome::Lost, U256::ZERO);
+        // About half, give or take rounding
+        let reduced = amount(&state);
+        assert!(reduced < usual, "{reduced} >= {usual}");
+        assert!(reduced >= percentage_of(usual, 3000), "{reduced} < 30% of {usual}");
+
+        // A win ends the streak
+        state.pnl.record_bet(100, U256::from(1), 200, 0);
+        state.pnl.resolve(100, BetOutcome::Won, U256::from(2));
+        assert_eq!(amount(&state), usual);
+    }
 }
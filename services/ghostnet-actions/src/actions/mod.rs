@@ -1,10 +1,35 @@
 //! Action decision and execution logic.
 //!
 //! This module contains the logic for deciding and executing actions
-//! on GhostCore and HashCrash contracts.
+//! on GhostCore, HashCrash, ArcadeCore and DeadPool contracts, for boosting
+//! GhostCore positions, for warming up new wallets and for winding down
+//! retiring ones.
 
+pub mod arcade;
+pub mod boost;
+pub mod dead_pool;
 pub mod ghost_core;
 pub mod hashcrash;
+pub mod retirement;
+pub mod warmup;
 
+pub use arcade::ArcadeDecider;
+pub use boost::BoostDecider;
+pub use dead_pool::DeadPoolDecider;
 pub use ghost_core::GhostCoreDecider;
 pub use hashcrash::HashCrashDecider;
+pub use retirement::RetirementDecider;
+pub use warmup::WarmupDecider;
+
+/// Every action the GHOSTNET plugin offers.
+pub const ACTIONS: [&str; 9] = [
+    ghost_core::ACTION_JACK_IN,
+    ghost_core::ACTION_ADD_STAKE,
+    ghost_core::ACTION_EXTRACT,
+    ghost_core::ACTION_CLAIM_REWARDS,
+    ghost_core::ACTION_APPROVE,
+    boost::ACTION_APPLY_BOOST,
+    hashcrash::ACTION_HASHCRASH_BET,
+    arcade::ACTION_ARCADE_PLAY,
+    dead_pool::ACTION_DEAD_POOL_BET,
+];
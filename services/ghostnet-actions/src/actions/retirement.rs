@@ -0,0 +1,101 @@
+//! Retirement decision logic.
+//!
+//! A retiring wallet (see [`Retirement`](fleet_core::wallet::Retirement))
+//! only gets out of GhostNet:
+//! - `extract`: Its alive position, in full, once out of the lock period
+//! - `claimRewards`: What a locked position earned so far
+//!
+//! Nothing is staked or bet. HashCrash bets already placed settle on their
+//! own once their round ends; the contract has no cash-out call to hurry
+//! them along.
+
+use alloy::primitives::U256;
+use fleet_core::plugins::Action;
+use tracing::debug;
+
+use crate::actions::ghost_core::{ACTION_CLAIM_REWARDS, ACTION_EXTRACT};
+use crate::state::GhostnetState;
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// DECISION LOGIC
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Decision logic for retiring wallets.
+pub struct RetirementDecider;
+
+impl RetirementDecider {
+    /// Decide how the wallet gets out of its position, if it has one.
+    ///
+    /// Returns `None` without an alive position, and while a locked one has
+    /// nothing to claim.
+    pub fn decide(state: &GhostnetState) -> Option<Action> {
+        let position = state.active_position()?;
+        if position.can_extract() {
+            debug!(amount = %position.amount, "Extracting position of retiring wallet");
+            return Some(Action::new(ACTION_EXTRACT, "Extract"));
+        }
+        if position.pending_rewards > U256::ZERO {
+            debug!("Claiming rewards of retiring wallet's locked position");
+            return Some(Action::new(ACTION_CLAIM_REWARDS, "Claim Rewards"));
+        }
+        None
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{Level, Position};
+
+    const DATA: u128 = 1_000_000_000_000_000_000;
+
+    fn state_with(alive: bool, in_lock_period: bool, rewards: u128) -> GhostnetState {
+        GhostnetState {
+            position: Some(Position {
+                amount: U256::from(50 * DATA),
+                level: Level::Darknet,
+                entry_timestamp: 0,
+                last_add_timestamp: 0,
+                alive,
+                ghost_streak: 0,
+                pending_rewards: U256::from(rewards * DATA),
+                effective_death_rate_bps: 3000,
+                in_lock_period,
+                active_boosts: Vec::new(),
+            }),
+            data_balance: U256::from(1000 * DATA),
+            ..GhostnetState::default()
+        }
+    }
+
+    fn decided(state: &GhostnetState) -> Option<String> {
+        RetirementDecider::decide(state).map(|action| action.id.0)
+    }
+
+    #[test]
+    fn only_exits() {
+        // A full extraction, however attractive holding is
+        assert_eq!(decided(&state_with(true, false, 0)).as_deref(), Some(ACTION_EXTRACT));
+        let action = RetirementDecider::decide(&state_with(true, false, 5));
+        assert!(action.is_some_and(|action| action.data.get("amount").is_none()));
+
+        // Locked positions only have their rewards claimed
+        assert_eq!(
+            decided(&state_with(true, true, 5)).as_deref(),
+            Some(ACTION_CLAIM_REWARDS)
+        );
+        assert_eq!(decided(&state_with(true, true, 0)), None);
+
+        // With plenty of DATA and no position, nothing is entered
+        assert_eq!(decided(&state_with(false, false, 0)), None);
+        let empty = GhostnetState {
+            data_balance: U256::from(1000 * DATA),
+            ..GhostnetState::default()
+        };
+        assert_eq!(decided(&empty), None);
+    }
+}
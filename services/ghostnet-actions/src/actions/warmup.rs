@@ -0,0 +1,251 @@
+//! Warm-up decision logic.
+//!
+//! While a wallet works through its [`WarmupPlan`], it only takes the plan's
+//! due step:
+//! - `stake`: A tiny `jackIn` at the lowest level
+//! - `bet`: A small HashCrash bet
+//! - `claim`: Claiming the position's rewards
+//!
+//! Steps the position already covers (a stake while one is live, a claim
+//! with nothing to claim) become a bet, and a claim without a position an
+//! entry. Amounts are scaled by the plan's factor and risk tolerance is
+//! scaled down with it, so targets and levels stay low early on.
+
+use alloy::primitives::U256;
+use fleet_core::plugins::{Action, PluginContext};
+use fleet_core::profiles::BehaviorProfile;
+use fleet_core::wallet::{WarmupPlan, WarmupStepKind};
+use tracing::debug;
+
+use crate::actions::ghost_core::{ACTION_CLAIM_REWARDS, ACTION_JACK_IN};
+use crate::actions::hashcrash::MIN_BET;
+use crate::actions::{GhostCoreDecider, HashCrashDecider};
+use crate::config::{BehaviorSettings, LevelSettings};
+use crate::math::{pct_to_bps, percentage_of};
+use crate::state::{GhostnetState, Level};
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// CONSTANTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Level of warm-up entries.
+const WARMUP_LEVEL: Level = Level::Vault;
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// DECISION LOGIC
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Decision logic for warming wallets.
+pub struct WarmupDecider;
+
+impl WarmupDecider {
+    /// Decide the action for the plan's due step, if any.
+    ///
+    /// Returns `None` between steps and when the wallet cannot take its step
+    /// (e.g. too little DATA or no open round); the plan stretches instead.
+    pub fn decide(
+        state: &GhostnetState,
+        plan: &WarmupPlan,
+        profile: &BehaviorProfile,
+        settings: &BehaviorSettings,
+        context: &mut PluginContext<'_>,
+    ) -> Option<Action> {
+        let step = plan.due_step(context.now)?;
+        let factor = plan.factor_at(context.now);
+
+        let mut cautious = profile.clone();
+        cautious.risk_tolerance *= factor;
+
+        let position = state.position.as_ref().filter(|position| position.alive);
+        let action = match (step, position) {
+            (WarmupStepKind::Claim, Some(position)) if !position.pending_rewards.is_zero() => {
+                Some(Action::new(ACTION_CLAIM_REWARDS, "Claim Rewards"))
+            }
+            (WarmupStepKind::Stake | WarmupStepKind::Claim, None) => {
+                Self::entry(state, &cautious, settings, factor, context)
+            }
+            _ => HashCrashDecider::decide(state, &cautious, settings, context)
+                .map(|bet| Self::scaled(bet, factor, U256::from(MIN_BET))),
+        };
+
+        if let Some(action) = &action {
+            debug!(step = ?step, factor, action = %action.id, "Deciding warm-up step");
+        }
+        action
+    }
+
+    /// A tiny entry at the warm-up level.
+    fn entry(
+        state: &GhostnetState,
+        profile: &BehaviorProfile,
+        settings: &BehaviorSettings,
+        factor: f64,
+        context: &mut PluginContext<'_>,
+    ) -> Option<Action> {
+        let min_stake = LevelSettings::for_level(WARMUP_LEVEL.as_u8())?.min_stake;
+        let amount = GhostCoreDecider::calculate_entry_amount(
+            state,
+            profile,
+            WARMUP_LEVEL,
+            settings,
+            context,
+        );
+        if amount.is_zero() {
+            return None;
+        }
+
+        let amount = percentage_of(amount, pct_to_bps(factor)).max(U256::from(min_stake));
+        Some(Action::with_data(
+            ACTION_JACK_IN,
+            "Jack In",
+            serde_json::json!({
+                "amount": amount.to_string(),
+                "level": WARMUP_LEVEL.as_u8(),
+            }),
+        ))
+    }
+
+    /// Scale an action's amount by `factor`, keeping at least `min`.
+    fn scaled(mut action: Action, factor: f64, min: U256) -> Action {
+        let amount = action.data["amount"]
+            .as_str()
+            .and_then(|amount| amount.parse::<U256>().ok());
+        if let Some(amount) = amount {
+            let scaled = percentage_of(amount, pct_to_bps(factor)).max(min);
+            action.data["amount"] = scaled.to_string().into();
+        }
+        action
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::state::{HashCrashRound, Position};
+    use chrono::{DateTime, Duration, Utc};
+    use fleet_core::wallet::WarmupStep;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    const DATA: u128 = 1_000_000_000_000_000_000;
+
+    fn plan(kind: WarmupStepKind, now: DateTime<Utc>) -> WarmupPlan {
+        WarmupPlan {
+            starts_at: now,
+            ends_at: now + Duration::days(4),
+            initial_factor: 0.1,
+            steps: vec![WarmupStep {
+                kind,
+                due_at: now + Duration::days(1),
+            }],
+            completed: 0,
+        }
+    }
+
+    fn position(pending_rewards: u128) -> Position {
+        Position {
+            amount: U256::from(10 * DATA),
+            level: Level::Vault,
+            entry_timestamp: 0,
+            last_add_timestamp: 0,
+            alive: true,
+            ghost_streak: 0,
+            pending_rewards: U256::from(pending_rewards),
+            effective_death_rate_bps: 500,
+            in_lock_period: false,
+            active_boosts: Vec::new(),
+        }
+    }
+
+    fn decide(state: &GhostnetState, plan: &WarmupPlan, at: DateTime<Utc>) -> Option<Action> {
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut context = PluginContext::new(at, &mut rng, &serde_json::Value::Null);
+        let profile = BehaviorProfile::degen();
+        WarmupDecider::decide(
+            state,
+            plan,
+            &profile,
+            &BehaviorSettings::default(),
+            &mut context,
+        )
+    }
+
+    #[test]
+    fn stakes_tiny_amounts_at_the_lowest_level() {
+        let now = Utc::now();
+        let plan = plan(WarmupStepKind::Stake, now);
+        let state = GhostnetState {
+            data_balance: U256::from(1000 * DATA),
+            ..GhostnetState::default()
+        };
+        let due = now + Duration::days(1);
+
+        assert!(decide(&state, &plan, now).is_none(), "step not due yet");
+
+        let action = decide(&state, &plan, due).expect("should enter");
+        assert_eq!(action.id.as_str(), ACTION_JACK_IN);
+        assert_eq!(action.data["level"], Level::Vault.as_u8());
+        // A degen normally stakes ~50%, warming up at a factor of 0.325
+        let amount: U256 = action.data["amount"].as_str().unwrap().parse().unwrap();
+        assert!(amount <= U256::from(200 * DATA), "{amount}");
+
+        // Too little DATA waits for the plan to stretch
+        let poor = GhostnetState {
+            data_balance: U256::from(DATA / 2),
+            ..GhostnetState::default()
+        };
+        assert!(decide(&poor, &plan, due).is_none());
+    }
+
+    #[test]
+    fn covered_steps_fall_back_to_small_bets() {
+        let now = Utc::now();
+        let due = now + Duration::days(1);
+        let mut state = GhostnetState {
+            data_balance: U256::from(1000 * DATA),
+            position: Some(position(2 * DATA)),
+            ..GhostnetState::default()
+        };
+
+        let claim = decide(&state, &plan(WarmupStepKind::Claim, now), due);
+        assert_eq!(claim.map(|a| a.id), Some(ACTION_CLAIM_REWARDS.into()));
+
+        // Nothing to claim and no round to bet on
+        state.position = Some(position(0));
+        assert!(decide(&state, &plan(WarmupStepKind::Claim, now), due).is_none());
+        assert!(decide(&state, &plan(WarmupStepKind::Stake, now), due).is_none());
+
+        state.hashcrash_round = Some(HashCrashRound {
+            round_id: 1,
+            is_betting: true,
+            betting_ends_at: u64::MAX,
+            player_count: 0,
+            prize_pool: U256::ZERO,
+        });
+        let bets: Vec<U256> = (0..50)
+            .filter_map(|seed| {
+                let mut rng = StdRng::seed_from_u64(seed);
+                let mut context = PluginContext::new(due, &mut rng, &serde_json::Value::Null);
+                WarmupDecider::decide(
+                    &state,
+                    &plan(WarmupStepKind::Stake, now),
+                    &BehaviorProfile::degen(),
+                    &BehaviorSettings::default(),
+                    &mut context,
+                )
+            })
+            .map(|bet| bet.data["amount"].as_str().unwrap().parse().unwrap())
+            .collect();
+        assert!(!bets.is_empty());
+        // Well below the 10% of balance a degen may bet
+        assert!(
+            bets.iter().all(|bet| *bet < U256::from(100 * DATA / 3)),
+            "{bets:?}"
+        );
+    }
+}
@@ -1,8 +1,15 @@
 //! Configuration for the GHOSTNET plugin.
 
-use alloy::primitives::Address;
+use std::collections::BTreeMap;
+
+use alloy::primitives::{Address, U256};
+use fleet_core::validation::ConfigReport;
 use serde::{Deserialize, Serialize};
 
+use crate::actions::ACTIONS;
+use crate::math::{BPS_100_PERCENT, DeathRateTable};
+use crate::state::BoostType;
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // GHOSTNET CONFIG
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -25,12 +32,24 @@ pub struct GhostnetConfig {
     /// DATA token contract address.
     pub data_token: Address,
 
+    /// DeadPool contract address, if its rounds are bet on.
+    #[serde(default)]
+    pub dead_pool: Option<Address>,
+
     /// Chain ID (6343 for MegaETH testnet, 4326 for mainnet).
     pub chain_id: u64,
 
     /// Behavior settings.
     #[serde(default)]
     pub behavior: BehaviorSettings,
+
+    /// Which ArcadeCore games may be played.
+    #[serde(default)]
+    pub arcade_games: GameFilter,
+
+    /// Gas limits of action transactions.
+    #[serde(default)]
+    pub gas: GasSettings,
 }
 
 impl GhostnetConfig {
@@ -48,8 +67,11 @@ impl GhostnetConfig {
             hash_crash,
             arcade_core,
             data_token,
+            dead_pool: None,
             chain_id,
             behavior: BehaviorSettings::default_const(),
+            arcade_games: GameFilter::new(),
+            gas: GasSettings::new(),
         }
     }
 
@@ -67,8 +89,564 @@ impl GhostnetConfig {
             hash_crash: Address::repeat_byte(0x02),
             arcade_core: Address::repeat_byte(0x03),
             data_token: Address::repeat_byte(0x04),
+            dead_pool: None,
             chain_id: 6343, // MegaETH testnet
             behavior: BehaviorSettings::default(),
+            arcade_games: GameFilter::default(),
+            gas: GasSettings::default(),
+        }
+    }
+
+    /// Check the config, naming keys by their path within it (e.g.
+    /// `behavior.max_boost_reward_share`).
+    ///
+    /// Contract addresses must be set and distinct, and the chain ID set.
+    /// The DeadPool address is optional.
+    #[must_use]
+    pub fn validate(&self) -> ConfigReport {
+        let mut report = ConfigReport::new();
+        let mut contracts = vec![
+            ("ghost_core", self.ghost_core),
+            ("hash_crash", self.hash_crash),
+            ("arcade_core", self.arcade_core),
+            ("data_token", self.data_token),
+        ];
+        contracts.extend(self.dead_pool.map(|address| ("dead_pool", address)));
+        for (i, (key, address)) in contracts.iter().enumerate() {
+            if address.is_zero() {
+                report.error(*key, "must not be the zero address");
+            } else if let Some((other, _)) = contracts[..i].iter().find(|(_, a)| a == address) {
+                report.error(*key, format!("is the same address as {other}"));
+            }
+        }
+        if self.chain_id == 0 {
+            report.error("chain_id", "must be > 0");
+        }
+        report.extend_under("behavior", self.behavior.validate());
+        report.extend_under("gas", self.gas.validate());
+        report.extend_under("arcade_games", self.arcade_games.validate());
+        report
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// GAME FILTER
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Allow and deny lists of ArcadeCore game IDs.
+///
+/// An empty allow list allows every game; the deny list wins over it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameFilter {
+    /// Games that may be played, all if empty.
+    #[serde(default)]
+    pub allow: Vec<u64>,
+
+    /// Games that are never played.
+    #[serde(default)]
+    pub deny: Vec<u64>,
+}
+
+impl GameFilter {
+    /// Create a filter that allows every game.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            allow: Vec::new(),
+            deny: Vec::new(),
+        }
+    }
+
+    /// Check if a game may be played.
+    #[must_use]
+    pub fn allows(&self, game_id: u64) -> bool {
+        (self.allow.is_empty() || self.allow.contains(&game_id)) && !self.deny.contains(&game_id)
+    }
+
+    /// Check the filter. Games on both lists are only warned about, as the
+    /// deny list wins.
+    #[must_use]
+    pub fn validate(&self) -> ConfigReport {
+        let mut report = ConfigReport::new();
+        let both: Vec<_> = self.allow.iter().filter(|id| self.deny.contains(id)).collect();
+        if !both.is_empty() {
+            report.warning("deny", format!("games {both:?} are also allowed, and stay denied"));
+        }
+        report
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// GAS SETTINGS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// How action transactions get their gas limit.
+///
+/// Each transaction's gas is estimated before sending, and the limit is the
+/// estimate plus a safety margin, capped at the action's maximum. Actions
+/// estimated to need more than their maximum are not sent.
+///
+/// ```toml
+/// [gas]
+/// margin_bps = 2000
+/// max_gas = 10000000
+/// action_max_gas = { "ghostnet.extract" = 500000 }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GasSettings {
+    /// Margin added to the estimate (basis points).
+    #[serde(default = "GasSettings::default_margin_bps")]
+    pub margin_bps: u32,
+
+    /// Most gas an action's transaction may be given.
+    #[serde(default = "GasSettings::default_max_gas")]
+    pub max_gas: u64,
+
+    /// Most gas by action ID, overriding `max_gas`.
+    #[serde(default)]
+    pub action_max_gas: BTreeMap<String, u64>,
+}
+
+impl Default for GasSettings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GasSettings {
+    /// Create the default gas settings.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            margin_bps: Self::default_margin_bps(),
+            max_gas: Self::default_max_gas(),
+            action_max_gas: BTreeMap::new(),
+        }
+    }
+
+    /// Default for [`margin_bps`](Self::margin_bps).
+    #[must_use]
+    pub const fn default_margin_bps() -> u32 {
+        2000 // 20%
+    }
+
+    /// Default for [`max_gas`](Self::max_gas): MegaETH's fixed gas limit,
+    /// which its provider reports as the estimate.
+    #[must_use]
+    pub const fn default_max_gas() -> u64 {
+        10_000_000
+    }
+
+    /// Check that every cap allows some gas and is of a known action.
+    #[must_use]
+    pub fn validate(&self) -> ConfigReport {
+        let mut report = ConfigReport::new();
+        if self.max_gas == 0 {
+            report.error("max_gas", "must be at least 1");
+        }
+        for (action, &max) in &self.action_max_gas {
+            let key = format!("action_max_gas.\"{action}\"");
+            if !ACTIONS.contains(&action.as_str()) {
+                report.error(key, format!("is not an action ({})", ACTIONS.join(", ")));
+            } else if max == 0 {
+                report.error(key, "must be at least 1");
+            }
+        }
+        report
+    }
+
+    /// Most gas the transaction of `action` may be given.
+    #[must_use]
+    pub fn max_gas_for(&self, action: &str) -> u64 {
+        self.action_max_gas.get(action).copied().unwrap_or(self.max_gas)
+    }
+
+    /// Gas limit of a transaction of `action` estimated at `estimate`, or
+    /// `None` if the estimate exceeds the action's maximum.
+    #[must_use]
+    pub fn gas_limit(&self, action: &str, estimate: u64) -> Option<u64> {
+        let max = self.max_gas_for(action);
+        if estimate > max {
+            return None;
+        }
+        let padded = u128::from(estimate)
+            .saturating_mul(u128::from(BPS_100_PERCENT) + u128::from(self.margin_bps))
+            .div_ceil(u128::from(BPS_100_PERCENT));
+        Some(u64::try_from(padded).map_or(max, |padded| padded.min(max)))
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// EXTRACT STRATEGY
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// How a position is taken out of GhostCore once extracting is worth it.
+///
+/// Partial extractions need a GhostCore that takes an amount to extract;
+/// against one that does not, every strategy extracts in full.
+///
+/// ```toml
+/// extract_strategy = { type = "full" }
+/// extract_strategy = { type = "take_profits", keep_bps = 5000 }
+/// extract_strategy = { type = "ladder", steps = 4 }
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ExtractStrategy {
+    /// Extract the whole position at once.
+    #[default]
+    Full,
+
+    /// Extract all but a base position first, and the base the next time.
+    TakeProfits {
+        /// Share of the stake kept as the base position (basis points).
+        keep_bps: u16,
+    },
+
+    /// Extract the stake in equal parts.
+    Ladder {
+        /// Number of parts.
+        steps: u8,
+    },
+}
+
+impl ExtractStrategy {
+    /// DATA to extract from a position staking `stake` (in wei), from which
+    /// `extracted` was extracted before, or `None` to extract all of it.
+    ///
+    /// Patient profiles take smaller parts, down to half at full patience.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)] // Clamped to 5000 - 10000
+    pub fn partial_amount(&self, stake: U256, extracted: U256, patience: f64) -> Option<U256> {
+        let part = match *self {
+            Self::Full => return None,
+            Self::TakeProfits { keep_bps } => {
+                if extracted > U256::ZERO {
+                    return None;
+                }
+                let keep_bps = u64::from(keep_bps).min(BPS_100_PERCENT);
+                stake * U256::from(BPS_100_PERCENT - keep_bps) / U256::from(BPS_100_PERCENT)
+            }
+            Self::Ladder { steps } => {
+                let step = stake.saturating_add(extracted) / U256::from(steps.max(1));
+                // The last step takes what is left
+                if stake <= step {
+                    return None;
+                }
+                step
+            }
+        };
+        let scale_bps = (patience.clamp(0.0, 1.0).mul_add(-0.5, 1.0) * 10_000.0).round() as u64;
+        let amount = part * U256::from(scale_bps) / U256::from(BPS_100_PERCENT);
+        (amount > U256::ZERO && amount < stake).then_some(amount)
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// STREAK PROTECTION
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Holding positions whose ghost streak is about to reach a milestone.
+///
+/// Within `window` scans of a milestone, voluntary extractions are held
+/// back, unless the position's culling risk reaches `max_cull_risk_bps` or
+/// the profile's patience is below `min_patience`. No milestones disables it.
+///
+/// ```toml
+/// streak_protection = { milestones = [10, 20], window = 2 }
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StreakProtection {
+    /// Ghost streaks worth holding a position for.
+    #[serde(default)]
+    pub milestones: Vec<u16>,
+
+    /// How many scans before a milestone extractions are held back.
+    #[serde(default = "StreakProtection::default_window")]
+    pub window: u16,
+
+    /// Culling risk from which a position is extracted anyway (basis points).
+    #[serde(default = "StreakProtection::default_max_cull_risk_bps")]
+    pub max_cull_risk_bps: u16,
+
+    /// Least patience of a profile that holds for milestones (0.0 - 1.0).
+    #[serde(default = "StreakProtection::default_min_patience")]
+    pub min_patience: f64,
+}
+
+impl Default for StreakProtection {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StreakProtection {
+    /// Create the default streak protection, without milestones.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            milestones: Vec::new(),
+            window: Self::default_window(),
+            max_cull_risk_bps: Self::default_max_cull_risk_bps(),
+            min_patience: Self::default_min_patience(),
+        }
+    }
+
+    /// Default for [`window`](Self::window).
+    #[must_use]
+    pub const fn default_window() -> u16 {
+        2
+    }
+
+    /// Default for [`max_cull_risk_bps`](Self::max_cull_risk_bps).
+    #[must_use]
+    pub const fn default_max_cull_risk_bps() -> u16 {
+        2500 // 25%
+    }
+
+    /// Default for [`min_patience`](Self::min_patience).
+    #[must_use]
+    pub const fn default_min_patience() -> f64 {
+        0.2
+    }
+
+    /// Milestone a position at ghost streak `streak` is held for, if any:
+    /// the nearest one it is at most `window` scans short of.
+    #[must_use]
+    pub fn milestone_ahead(&self, streak: u16) -> Option<u16> {
+        self.milestones
+            .iter()
+            .copied()
+            .filter(|&milestone| milestone > streak && milestone - streak <= self.window)
+            .min()
+    }
+
+    /// Check that the culling risk is at most 100% and patience a share.
+    #[must_use]
+    pub fn validate(&self) -> ConfigReport {
+        let mut report = ConfigReport::new();
+        if u64::from(self.max_cull_risk_bps) > BPS_100_PERCENT {
+            report.error(
+                "max_cull_risk_bps",
+                format!("must be at most {BPS_100_PERCENT}, got {}", self.max_cull_risk_bps),
+            );
+        }
+        if !(0.0..=1.0).contains(&self.min_patience) {
+            report.error(
+                "min_patience",
+                format!("must be between 0.0 and 1.0, got {}", self.min_patience),
+            );
+        }
+        if !self.milestones.is_empty() && self.window == 0 {
+            report.warning("window", "is 0, so no position is held for its milestones");
+        }
+        report
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// DEADPOOL SETTINGS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// How DeadPool rounds are picked and bet on.
+///
+/// DeadPool is parimutuel: the winning side splits the pot, so a side pays
+/// more the less was bet on it. A bet goes on the side whose expected
+/// value, from its payout and the round type's prior, beats an even bet by
+/// at least `min_edge_bps`. Profiles at least `late_min_patience` patient
+/// only bet in the last `late_window` of a round's betting window, once the
+/// crowd has shown its hand. Before a bet is sent its round is read again,
+/// and the bet is dropped if its payout fell by more than
+/// `max_odds_drift_bps`.
+///
+/// ```toml
+/// [behavior.dead_pool]
+/// priors = { whale_death = 0.3 }
+/// min_edge_bps = 500
+/// late_window = 0.2
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeadPoolSettings {
+    /// Chance of OVER winning, per round type.
+    #[serde(default)]
+    pub priors: RoundPriors,
+
+    /// How much a bet's expected value must beat an even bet by (basis
+    /// points of the bet).
+    #[serde(default = "DeadPoolSettings::default_min_edge_bps")]
+    pub min_edge_bps: u64,
+
+    /// Maximum percentage of balance to bet on a round (0.0 - 1.0).
+    #[serde(default = "DeadPoolSettings::default_max_bet_pct")]
+    pub max_bet_pct: f64,
+
+    /// Least patience of a profile that bets late (0.0 - 1.0).
+    #[serde(default = "DeadPoolSettings::default_late_min_patience")]
+    pub late_min_patience: f64,
+
+    /// Final share of a round's betting window late bettors bet in
+    /// (0.0 - 1.0).
+    #[serde(default = "DeadPoolSettings::default_late_window")]
+    pub late_window: f64,
+
+    /// How far a bet's payout may fall between deciding and sending it
+    /// (basis points of the payout).
+    #[serde(default = "DeadPoolSettings::default_max_odds_drift_bps")]
+    pub max_odds_drift_bps: u64,
+
+    /// How many of the latest rounds are checked for open ones.
+    #[serde(default = "DeadPoolSettings::default_lookback_rounds")]
+    pub lookback_rounds: u64,
+}
+
+impl Default for DeadPoolSettings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DeadPoolSettings {
+    /// Create the default DeadPool settings.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            priors: RoundPriors::new(),
+            min_edge_bps: Self::default_min_edge_bps(),
+            max_bet_pct: Self::default_max_bet_pct(),
+            late_min_patience: Self::default_late_min_patience(),
+            late_window: Self::default_late_window(),
+            max_odds_drift_bps: Self::default_max_odds_drift_bps(),
+            lookback_rounds: Self::default_lookback_rounds(),
+        }
+    }
+
+    /// Default for [`min_edge_bps`](Self::min_edge_bps).
+    #[must_use]
+    pub const fn default_min_edge_bps() -> u64 {
+        500 // 5%
+    }
+
+    /// Default for [`max_bet_pct`](Self::max_bet_pct).
+    #[must_use]
+    pub const fn default_max_bet_pct() -> f64 {
+        0.02
+    }
+
+    /// Default for [`late_min_patience`](Self::late_min_patience).
+    #[must_use]
+    pub const fn default_late_min_patience() -> f64 {
+        0.7
+    }
+
+    /// Default for [`late_window`](Self::late_window).
+    #[must_use]
+    pub const fn default_late_window() -> f64 {
+        0.2
+    }
+
+    /// Default for [`max_odds_drift_bps`](Self::max_odds_drift_bps).
+    #[must_use]
+    pub const fn default_max_odds_drift_bps() -> u64 {
+        1000 // 10%
+    }
+
+    /// Default for [`lookback_rounds`](Self::lookback_rounds).
+    #[must_use]
+    pub const fn default_lookback_rounds() -> u64 {
+        5
+    }
+
+    /// Check that shares and priors are within 0.0-1.0 and the drift at most
+    /// 100%.
+    #[must_use]
+    pub fn validate(&self) -> ConfigReport {
+        let mut report = ConfigReport::new();
+        for (key, share) in [
+            ("max_bet_pct", self.max_bet_pct),
+            ("late_min_patience", self.late_min_patience),
+            ("late_window", self.late_window),
+            ("priors.death_count", self.priors.death_count),
+            ("priors.whale_death", self.priors.whale_death),
+            ("priors.streak_record", self.priors.streak_record),
+            ("priors.system_reset", self.priors.system_reset),
+        ] {
+            if !(0.0..=1.0).contains(&share) {
+                report.error(key, format!("must be between 0.0 and 1.0, got {share}"));
+            }
+        }
+        if self.max_odds_drift_bps > BPS_100_PERCENT {
+            report.error(
+                "max_odds_drift_bps",
+                format!(
+                    "must be at most {BPS_100_PERCENT}, got {}",
+                    self.max_odds_drift_bps
+                ),
+            );
+        }
+        if self.lookback_rounds == 0 {
+            report.warning("lookback_rounds", "is 0, so no round is bet on");
+        }
+        report
+    }
+}
+
+/// Chance of OVER winning a DeadPool round, per round type (0.0 - 1.0).
+///
+/// Rounds that ask a yes/no question, such as whether a whale dies, count
+/// yes as OVER. The defaults take either side to be as likely.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RoundPriors {
+    /// Over/under deaths in the next scan.
+    #[serde(default = "RoundPriors::even")]
+    pub death_count: f64,
+
+    /// Whether a 1000+ DATA position dies.
+    #[serde(default = "RoundPriors::even")]
+    pub whale_death: f64,
+
+    /// Whether anyone reaches a 20 scan survival streak.
+    #[serde(default = "RoundPriors::even")]
+    pub streak_record: f64,
+
+    /// Whether the system reset timer drops below an hour.
+    #[serde(default = "RoundPriors::even")]
+    pub system_reset: f64,
+}
+
+impl Default for RoundPriors {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RoundPriors {
+    /// Create priors that take either side to be as likely.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            death_count: Self::even(),
+            whale_death: Self::even(),
+            streak_record: Self::even(),
+            system_reset: Self::even(),
+        }
+    }
+
+    /// Default for every round type.
+    #[must_use]
+    pub const fn even() -> f64 {
+        0.5
+    }
+
+    /// Chance of OVER winning a round of `round_type`, or `None` for a type
+    /// DeadPool does not have.
+    #[must_use]
+    pub const fn over_chance(&self, round_type: u8) -> Option<f64> {
+        match round_type {
+            0 => Some(self.death_count),
+            1 => Some(self.whale_death),
+            2 => Some(self.streak_record),
+            3 => Some(self.system_reset),
+            _ => None,
         }
     }
 }
@@ -82,6 +660,7 @@ impl GhostnetConfig {
 /// These are translated from the generic `BehaviorProfile` into
 /// GHOSTNET-specific thresholds and probabilities.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[allow(clippy::struct_excessive_bools)] // Independent toggles, not states
 pub struct BehaviorSettings {
     /// Minimum DATA balance to consider entering a position (in wei).
     /// Wallets with less than this will not attempt to jack in.
@@ -102,6 +681,121 @@ pub struct BehaviorSettings {
 
     /// Maximum percentage of balance to bet on HashCrash (0.0 - 1.0).
     pub max_hashcrash_bet_pct: f64,
+
+    /// Whether to play the other ArcadeCore games.
+    #[serde(default = "BehaviorSettings::default_plays_arcade")]
+    pub plays_arcade: bool,
+
+    /// Maximum percentage of balance to enter into an arcade game (0.0 - 1.0).
+    #[serde(default = "BehaviorSettings::default_max_arcade_bet_pct")]
+    pub max_arcade_bet_pct: f64,
+
+    /// HashCrash losses in a row after which bets are cut down (0 disables).
+    #[serde(default = "BehaviorSettings::default_hashcrash_loss_streak")]
+    pub hashcrash_loss_streak: u32,
+
+    /// Fraction of the usual bet size to bet after a losing streak (0.0 - 1.0).
+    #[serde(default = "BehaviorSettings::default_hashcrash_loss_streak_bet_pct")]
+    pub hashcrash_loss_streak_bet_pct: f64,
+
+    /// Whether to apply death reduction boosts.
+    #[serde(default = "BehaviorSettings::default_boosts_enabled")]
+    pub death_reduction_boosts: bool,
+
+    /// Whether to apply yield multiplier boosts.
+    #[serde(default = "BehaviorSettings::default_boosts_enabled")]
+    pub yield_multiplier_boosts: bool,
+
+    /// Most DATA a wallet spends on boosts in total (in wei).
+    #[serde(default = "BehaviorSettings::default_max_boost_spend")]
+    pub max_boost_spend: u128,
+
+    /// Share of pending rewards a fully risk tolerant profile pays at most for
+    /// one boost (0.0 - 1.0). Scaled down by the profile's risk tolerance.
+    #[serde(default = "BehaviorSettings::default_max_boost_reward_share")]
+    pub max_boost_reward_share: f64,
+
+    /// Maximum age of the DATA balance to size stakes and bets from (seconds).
+    /// Older balances trigger a refresh request instead of an action.
+    #[serde(default = "BehaviorSettings::default_max_balance_age_secs")]
+    pub max_balance_age_secs: u64,
+
+    /// DATA kept in the wallet when sizing stakes and bets (in wei).
+    #[serde(default)]
+    pub data_reserve: u128,
+
+    /// Death rates to value positions with, per level (defaults to the
+    /// protocol's base rates).
+    #[serde(default)]
+    pub death_rates: DeathRateTable,
+
+    /// How much more extracting must be worth than holding for one more scan
+    /// before a fully patient profile extracts (basis points of the exit
+    /// value). Scaled down by the profile's patience.
+    #[serde(default = "BehaviorSettings::default_min_extract_edge_bps")]
+    pub min_extract_edge_bps: u64,
+
+    /// Least DATA an extraction must bring in after its toll and gas before
+    /// it is worth it (in wei). Ignored for positions about to be culled.
+    #[serde(default = "BehaviorSettings::default_min_net_extract")]
+    pub min_net_extract: u128,
+
+    /// Estimated gas cost of an extraction, priced in DATA (in wei).
+    #[serde(default)]
+    pub extract_gas_cost: u128,
+
+    /// Culling risk from which a position is extracted whatever it brings in
+    /// (basis points).
+    #[serde(default = "BehaviorSettings::default_imminent_cull_risk_bps")]
+    pub imminent_cull_risk_bps: u16,
+
+    /// Exit toll assumed while GhostCore's cannot be read (basis points).
+    #[serde(default)]
+    pub default_exit_toll_bps: u16,
+
+    /// How long an exit toll read from GhostCore is reused (seconds).
+    #[serde(default = "BehaviorSettings::default_exit_toll_ttl_secs")]
+    pub exit_toll_ttl_secs: u64,
+
+    /// How close GhostCore's system reset may be before wallets stop
+    /// entering and topping up positions and extract theirs (seconds,
+    /// 0 disables).
+    #[serde(default = "BehaviorSettings::default_reset_danger_secs")]
+    pub reset_danger_secs: u64,
+
+    /// How long a reset timer read from GhostCore is reused (seconds).
+    #[serde(default = "BehaviorSettings::default_reset_timer_ttl_secs")]
+    pub reset_timer_ttl_secs: u64,
+
+    /// How long DataToken's tax rate and exclusions are reused (seconds).
+    #[serde(default = "BehaviorSettings::default_transfer_tax_ttl_secs")]
+    pub transfer_tax_ttl_secs: u64,
+
+    /// How positions are extracted.
+    #[serde(default)]
+    pub extract_strategy: ExtractStrategy,
+
+    /// Whether stakes GhostCore's DATA allowance does not cover are preceded
+    /// by an approval, with the stake as its follow-up.
+    #[serde(default)]
+    pub approve_before_stake: bool,
+
+    /// Probability of re-entering at another level shortly after a full
+    /// extraction (0.0 - 1.0).
+    #[serde(default)]
+    pub reentry_probability: f64,
+
+    /// Holding positions close to a ghost streak milestone.
+    #[serde(default)]
+    pub streak_protection: StreakProtection,
+
+    /// Whether to bet on DeadPool rounds, if a DeadPool is configured.
+    #[serde(default = "BehaviorSettings::default_plays_dead_pool")]
+    pub plays_dead_pool: bool,
+
+    /// How DeadPool rounds are picked and bet on.
+    #[serde(default)]
+    pub dead_pool: DeadPoolSettings,
 }
 
 impl Default for BehaviorSettings {
@@ -121,7 +815,205 @@ impl BehaviorSettings {
             base_compound_probability: 0.2,
             plays_hashcrash: true,
             max_hashcrash_bet_pct: 0.05, // 5% max per bet
+            plays_arcade: Self::default_plays_arcade(),
+            max_arcade_bet_pct: Self::default_max_arcade_bet_pct(),
+            hashcrash_loss_streak: Self::default_hashcrash_loss_streak(),
+            hashcrash_loss_streak_bet_pct: Self::default_hashcrash_loss_streak_bet_pct(),
+            death_reduction_boosts: Self::default_boosts_enabled(),
+            yield_multiplier_boosts: Self::default_boosts_enabled(),
+            max_boost_spend: Self::default_max_boost_spend(),
+            max_boost_reward_share: Self::default_max_boost_reward_share(),
+            max_balance_age_secs: Self::default_max_balance_age_secs(),
+            data_reserve: 0,
+            death_rates: DeathRateTable::new(),
+            min_extract_edge_bps: Self::default_min_extract_edge_bps(),
+            min_net_extract: Self::default_min_net_extract(),
+            extract_gas_cost: 0,
+            imminent_cull_risk_bps: Self::default_imminent_cull_risk_bps(),
+            default_exit_toll_bps: 0,
+            exit_toll_ttl_secs: Self::default_exit_toll_ttl_secs(),
+            reset_danger_secs: Self::default_reset_danger_secs(),
+            reset_timer_ttl_secs: Self::default_reset_timer_ttl_secs(),
+            transfer_tax_ttl_secs: Self::default_transfer_tax_ttl_secs(),
+            extract_strategy: ExtractStrategy::Full,
+            approve_before_stake: false,
+            reentry_probability: 0.0,
+            streak_protection: StreakProtection::new(),
+            plays_dead_pool: Self::default_plays_dead_pool(),
+            dead_pool: DeadPoolSettings::new(),
+        }
+    }
+
+    /// Check that shares are within 0.0-1.0 and basis points at most 100%.
+    #[must_use]
+    pub fn validate(&self) -> ConfigReport {
+        let mut report = ConfigReport::new();
+        for (key, share) in [
+            ("base_extract_probability", self.base_extract_probability),
+            ("base_compound_probability", self.base_compound_probability),
+            ("max_hashcrash_bet_pct", self.max_hashcrash_bet_pct),
+            ("max_arcade_bet_pct", self.max_arcade_bet_pct),
+            ("hashcrash_loss_streak_bet_pct", self.hashcrash_loss_streak_bet_pct),
+            ("max_boost_reward_share", self.max_boost_reward_share),
+            ("reentry_probability", self.reentry_probability),
+        ] {
+            if !(0.0..=1.0).contains(&share) {
+                report.error(key, format!("must be between 0.0 and 1.0, got {share}"));
+            }
+        }
+        for (key, bps) in [
+            ("min_extract_edge_bps", self.min_extract_edge_bps),
+            ("imminent_cull_risk_bps", u64::from(self.imminent_cull_risk_bps)),
+            ("default_exit_toll_bps", u64::from(self.default_exit_toll_bps)),
+        ] {
+            if bps > BPS_100_PERCENT {
+                report.error(key, format!("must be at most {BPS_100_PERCENT}, got {bps}"));
+            }
         }
+        match self.extract_strategy {
+            ExtractStrategy::TakeProfits { keep_bps } if u64::from(keep_bps) > BPS_100_PERCENT => {
+                report.error(
+                    "extract_strategy.keep_bps",
+                    format!("must be at most {BPS_100_PERCENT}"),
+                );
+            }
+            ExtractStrategy::Ladder { steps: 0 } => {
+                report.error("extract_strategy.steps", "must be at least 1");
+            }
+            _ => {}
+        }
+        report.extend_under("streak_protection", self.streak_protection.validate());
+        report.extend_under("dead_pool", self.dead_pool.validate());
+        report
+    }
+
+    /// Default for [`min_net_extract`](Self::min_net_extract).
+    #[must_use]
+    pub const fn default_min_net_extract() -> u128 {
+        1_000_000_000_000_000_000 // 1 DATA
+    }
+
+    /// Default for [`imminent_cull_risk_bps`](Self::imminent_cull_risk_bps).
+    #[must_use]
+    pub const fn default_imminent_cull_risk_bps() -> u16 {
+        5000 // 50%
+    }
+
+    /// Default for [`exit_toll_ttl_secs`](Self::exit_toll_ttl_secs).
+    #[must_use]
+    pub const fn default_exit_toll_ttl_secs() -> u64 {
+        300
+    }
+
+    /// Default for [`reset_danger_secs`](Self::reset_danger_secs).
+    #[must_use]
+    pub const fn default_reset_danger_secs() -> u64 {
+        900 // 15 minutes
+    }
+
+    /// Default for [`reset_timer_ttl_secs`](Self::reset_timer_ttl_secs).
+    #[must_use]
+    pub const fn default_reset_timer_ttl_secs() -> u64 {
+        30
+    }
+
+    /// Default for [`transfer_tax_ttl_secs`](Self::transfer_tax_ttl_secs).
+    #[must_use]
+    pub const fn default_transfer_tax_ttl_secs() -> u64 {
+        3600 // 1 hour
+    }
+
+    /// Default for [`min_extract_edge_bps`](Self::min_extract_edge_bps).
+    #[must_use]
+    pub const fn default_min_extract_edge_bps() -> u64 {
+        500 // 5%
+    }
+
+    /// Default for [`max_balance_age_secs`](Self::max_balance_age_secs).
+    #[must_use]
+    pub const fn default_max_balance_age_secs() -> u64 {
+        120
+    }
+
+    /// Default for [`plays_arcade`](Self::plays_arcade).
+    #[must_use]
+    pub const fn default_plays_arcade() -> bool {
+        true
+    }
+
+    /// Default for [`plays_dead_pool`](Self::plays_dead_pool).
+    #[must_use]
+    pub const fn default_plays_dead_pool() -> bool {
+        true
+    }
+
+    /// Default for [`max_arcade_bet_pct`](Self::max_arcade_bet_pct).
+    #[must_use]
+    pub const fn default_max_arcade_bet_pct() -> f64 {
+        0.05
+    }
+
+    /// Default for [`hashcrash_loss_streak`](Self::hashcrash_loss_streak).
+    #[must_use]
+    pub const fn default_hashcrash_loss_streak() -> u32 {
+        3
+    }
+
+    /// Default for [`hashcrash_loss_streak_bet_pct`](Self::hashcrash_loss_streak_bet_pct).
+    #[must_use]
+    pub const fn default_hashcrash_loss_streak_bet_pct() -> f64 {
+        0.5
+    }
+
+    /// Default for the per-type boost flags: every boost type is enabled.
+    #[must_use]
+    pub const fn default_boosts_enabled() -> bool {
+        true
+    }
+
+    /// Default for [`max_boost_spend`](Self::max_boost_spend).
+    #[must_use]
+    pub const fn default_max_boost_spend() -> u128 {
+        50_000_000_000_000_000_000 // 50 DATA
+    }
+
+    /// Default for [`max_boost_reward_share`](Self::max_boost_reward_share).
+    #[must_use]
+    pub const fn default_max_boost_reward_share() -> f64 {
+        0.5
+    }
+
+    /// Check if boosts of this type may be applied.
+    #[must_use]
+    pub const fn boost_enabled(&self, boost_type: BoostType) -> bool {
+        match boost_type {
+            BoostType::DeathReduction => self.death_reduction_boosts,
+            BoostType::YieldMultiplier => self.yield_multiplier_boosts,
+        }
+    }
+
+    /// [`max_balance_age_secs`](Self::max_balance_age_secs) as a duration.
+    #[must_use]
+    pub fn max_balance_age(&self) -> chrono::Duration {
+        chrono::Duration::seconds(i64::try_from(self.max_balance_age_secs).unwrap_or(i64::MAX))
+    }
+
+    /// [`exit_toll_ttl_secs`](Self::exit_toll_ttl_secs) as a duration.
+    #[must_use]
+    pub fn exit_toll_ttl(&self) -> chrono::Duration {
+        chrono::Duration::seconds(i64::try_from(self.exit_toll_ttl_secs).unwrap_or(i64::MAX))
+    }
+
+    /// [`reset_timer_ttl_secs`](Self::reset_timer_ttl_secs) as a duration.
+    #[must_use]
+    pub fn reset_timer_ttl(&self) -> chrono::Duration {
+        chrono::Duration::seconds(i64::try_from(self.reset_timer_ttl_secs).unwrap_or(i64::MAX))
+    }
+
+    /// [`transfer_tax_ttl_secs`](Self::transfer_tax_ttl_secs) as a duration.
+    #[must_use]
+    pub fn transfer_tax_ttl(&self) -> chrono::Duration {
+        chrono::Duration::seconds(i64::try_from(self.transfer_tax_ttl_secs).unwrap_or(i64::MAX))
     }
 }
 
@@ -184,6 +1076,7 @@ impl LevelSettings {
 // ═══════════════════════════════════════════════════════════════════════════════
 
 #[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
 mod tests {
     use super::*;
 
@@ -194,6 +1087,178 @@ mod tests {
         assert_ne!(config.ghost_core, Address::ZERO);
     }
 
+    #[test]
+    fn validation_names_each_broken_key() {
+        assert!(GhostnetConfig::testnet().validate().is_empty());
+
+        let mut config = GhostnetConfig::testnet();
+        config.hash_crash = Address::ZERO;
+        config.data_token = config.ghost_core;
+        config.chain_id = 0;
+        config.behavior.max_boost_reward_share = 1.5;
+        config.behavior.extract_strategy = ExtractStrategy::Ladder { steps: 0 };
+        config.gas.action_max_gas.insert("ghostnet.jack_out".into(), 100_000);
+        config.arcade_games = GameFilter {
+            allow: vec![1, 2],
+            deny: vec![2],
+        };
+
+        let report = config.validate();
+        let errors: Vec<_> = report.errors().map(|issue| issue.path.as_str()).collect();
+        assert_eq!(
+            errors,
+            [
+                "hash_crash",
+                "data_token",
+                "chain_id",
+                "behavior.max_boost_reward_share",
+                "behavior.extract_strategy.steps",
+                "gas.action_max_gas.\"ghostnet.jack_out\"",
+            ]
+        );
+        assert!(report.issues()[1].message.contains("same address as ghost_core"));
+        let warnings: Vec<_> = report.warnings().map(|issue| issue.path.as_str()).collect();
+        assert_eq!(warnings, ["arcade_games.deny"]);
+    }
+
+    #[test]
+    fn game_filter_denies_over_allowing() {
+        assert!(GameFilter::new().allows(7));
+
+        let filter = GameFilter {
+            allow: vec![1, 2],
+            deny: vec![2],
+        };
+        assert!(filter.allows(1));
+        assert!(!filter.allows(2), "deny list wins");
+        assert!(!filter.allows(3), "not on the allow list");
+    }
+
+    #[test]
+    fn gas_limit_adds_the_margin_up_to_the_cap() {
+        let mut gas = GasSettings::new();
+        assert_eq!(gas.gas_limit("ghostnet.jack_in", 100_000), Some(120_000));
+
+        // The margin is capped, estimates over the cap are refused
+        gas.action_max_gas.insert("ghostnet.extract".into(), 110_000);
+        assert_eq!(gas.gas_limit("ghostnet.extract", 100_000), Some(110_000));
+        assert_eq!(gas.gas_limit("ghostnet.extract", 110_001), None);
+        assert_eq!(gas.gas_limit("ghostnet.jack_in", 50_000_000), None);
+
+        let gas: GasSettings = serde_json::from_value(serde_json::json!({
+            "action_max_gas": { "ghostnet.extract": 500_000 },
+        }))
+        .unwrap();
+        assert_eq!(gas.margin_bps, 2000);
+        assert_eq!(gas.max_gas_for("ghostnet.extract"), 500_000);
+        assert_eq!(gas.max_gas_for("ghostnet.jack_in"), 10_000_000);
+    }
+
+    const DATA: u64 = 1_000_000_000_000_000_000;
+
+    fn data(tokens: u64) -> U256 {
+        U256::from(tokens) * U256::from(DATA)
+    }
+
+    #[test]
+    fn full_extracts_everything() {
+        assert_eq!(ExtractStrategy::Full.partial_amount(data(100), U256::ZERO, 0.0), None);
+    }
+
+    #[test]
+    fn take_profits_keeps_a_base_position() {
+        let strategy = ExtractStrategy::TakeProfits { keep_bps: 4000 };
+        assert_eq!(strategy.partial_amount(data(100), U256::ZERO, 0.0), Some(data(60)));
+
+        // Patience halves the profits taken at most
+        assert_eq!(strategy.partial_amount(data(100), U256::ZERO, 1.0), Some(data(30)));
+        assert_eq!(strategy.partial_amount(data(100), U256::ZERO, 0.5), Some(data(45)));
+
+        // Once profits were taken, the base goes too
+        assert_eq!(strategy.partial_amount(data(40), data(60), 0.0), None);
+
+        // Keeping nothing, or everything, is no partial extraction
+        let nothing = ExtractStrategy::TakeProfits { keep_bps: 0 };
+        assert_eq!(nothing.partial_amount(data(100), U256::ZERO, 0.0), None);
+        let everything = ExtractStrategy::TakeProfits { keep_bps: 10_000 };
+        assert_eq!(everything.partial_amount(data(100), U256::ZERO, 0.0), None);
+    }
+
+    #[test]
+    fn ladder_extracts_in_equal_steps() {
+        let strategy = ExtractStrategy::Ladder { steps: 4 };
+
+        // 100 DATA in steps of 25, the last one taking the rest
+        assert_eq!(strategy.partial_amount(data(100), U256::ZERO, 0.0), Some(data(25)));
+        assert_eq!(strategy.partial_amount(data(75), data(25), 0.0), Some(data(25)));
+        assert_eq!(strategy.partial_amount(data(50), data(50), 0.0), Some(data(25)));
+        assert_eq!(strategy.partial_amount(data(25), data(75), 0.0), None);
+
+        // Patient profiles take smaller steps
+        let half_step = data(25) / U256::from(2);
+        assert_eq!(strategy.partial_amount(data(100), U256::ZERO, 1.0), Some(half_step));
+
+        // A single step is a full extraction
+        let single = ExtractStrategy::Ladder { steps: 1 };
+        assert_eq!(single.partial_amount(data(100), U256::ZERO, 0.0), None);
+        let none = ExtractStrategy::Ladder { steps: 0 };
+        assert_eq!(none.partial_amount(data(100), U256::ZERO, 0.0), None);
+    }
+
+    #[test]
+    fn extract_strategy_is_tagged_by_type() {
+        let strategy: ExtractStrategy =
+            serde_json::from_value(serde_json::json!({ "type": "ladder", "steps": 3 })).unwrap();
+        assert_eq!(strategy, ExtractStrategy::Ladder { steps: 3 });
+        let strategy: ExtractStrategy =
+            serde_json::from_value(serde_json::json!({ "type": "take_profits", "keep_bps": 5000 }))
+                .unwrap();
+        assert_eq!(strategy, ExtractStrategy::TakeProfits { keep_bps: 5000 });
+    }
+
+    #[test]
+    fn streak_protection_holds_for_the_nearest_milestone_ahead() {
+        let protection: StreakProtection =
+            serde_json::from_value(serde_json::json!({ "milestones": [20, 10] })).unwrap();
+        assert_eq!(protection.window, 2);
+        assert_eq!(protection.milestone_ahead(7), None);
+        assert_eq!(protection.milestone_ahead(8), Some(10));
+        assert_eq!(protection.milestone_ahead(9), Some(10));
+        assert_eq!(protection.milestone_ahead(10), None, "reached");
+        assert_eq!(protection.milestone_ahead(19), Some(20));
+        assert_eq!(StreakProtection::new().milestone_ahead(9), None);
+
+        let mut settings = BehaviorSettings::default();
+        settings.streak_protection.min_patience = 1.5;
+        let report = settings.validate();
+        let errors: Vec<_> = report.errors().map(|issue| issue.path.as_str()).collect();
+        assert_eq!(errors, ["streak_protection.min_patience"]);
+    }
+
+    #[test]
+    fn dead_pool_is_optional_but_distinct() {
+        let mut config = GhostnetConfig::testnet();
+        config.dead_pool = Some(Address::repeat_byte(0x05));
+        assert!(config.validate().is_empty());
+
+        config.dead_pool = Some(config.hash_crash);
+        config.behavior.dead_pool.priors.whale_death = 1.2;
+        let report = config.validate();
+        let errors: Vec<_> = report.errors().map(|issue| issue.path.as_str()).collect();
+        assert_eq!(
+            errors,
+            ["dead_pool", "behavior.dead_pool.priors.whale_death"]
+        );
+
+        let settings: DeadPoolSettings =
+            serde_json::from_value(serde_json::json!({ "priors": { "whale_death": 0.3 } }))
+                .unwrap();
+        assert_eq!(settings.priors.over_chance(1), Some(0.3));
+        assert_eq!(settings.priors.over_chance(0), Some(0.5));
+        assert_eq!(settings.priors.over_chance(4), None);
+        assert_eq!(settings.min_edge_bps, 500);
+    }
+
     #[test]
     fn level_settings_exist_for_all_levels() {
         for level in 1..=5 {
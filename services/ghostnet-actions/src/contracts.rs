@@ -1,91 +1,21 @@
 //! Contract bindings for GHOSTNET.
 //!
-//! This module provides type-safe interfaces to the GHOSTNET smart contracts
-//! using Alloy's sol! macro for ABI generation.
+//! This module builds calldata for the GHOSTNET contracts and decodes the
+//! views the plugin reads, with the shared bindings from [`ghostnet_abi`].
+//! Events in action receipts are parsed in [`receipt`].
+
+pub mod receipt;
 
 use alloy::primitives::{Address, Bytes, U256};
-use alloy::sol;
 use alloy::sol_types::SolCall;
+use ghostnet_abi::{arcade_core, data_token, dead_pool, ghost_core, hash_crash};
 
 use crate::config::GhostnetConfig;
-use crate::state::Level;
-
-// ═══════════════════════════════════════════════════════════════════════════════
-// CONTRACT ABI DEFINITIONS
-// ═══════════════════════════════════════════════════════════════════════════════
-
-// GhostCore - Main staking contract
-sol! {
-    #[sol(rpc)]
-    interface IGhostCore {
-        // === Core Functions ===
-        function jackIn(uint256 amount, uint8 level) external;
-        function addStake(uint256 amount) external;
-        function extract() external returns (uint256 amount, uint256 rewards);
-        function claimRewards() external returns (uint256 rewards);
-
-        // === View Functions ===
-        function getPosition(address user) external view returns (
-            uint256 amount,
-            uint8 level,
-            uint64 entryTimestamp,
-            uint64 lastAddTimestamp,
-            uint256 rewardDebt,
-            bool alive,
-            uint16 ghostStreak
-        );
-        function getPendingRewards(address user) external view returns (uint256);
-        function getEffectiveDeathRate(address user) external view returns (uint16);
-        function isInLockPeriod(address user) external view returns (bool);
-        function isAlive(address user) external view returns (bool);
-    }
-}
-
-// HashCrash - Arcade crash game
-sol! {
-    #[sol(rpc)]
-    interface IHashCrash {
-        // === Core Functions ===
-        function placeBet(uint256 amount, uint256 targetMultiplier) external;
-
-        // === View Functions ===
-        function getCurrentRound() external view returns (
-            uint256 roundId,
-            uint8 state,
-            uint64 bettingEndsAt,
-            uint256 seedBlock,
-            uint256 crashMultiplier,
-            uint256 totalPrizePool,
-            uint256 playerCount
-        );
-        function getPlayerBet(uint256 roundId, address player) external view returns (
-            uint256 amount,
-            uint256 netAmount,
-            uint256 targetMultiplier,
-            bool settled
-        );
-    }
-}
-
-// ArcadeCore - Game management
-sol! {
-    #[sol(rpc)]
-    interface IArcadeCore {
-        function getPendingPayout(address player) external view returns (uint256);
-        function withdrawPayout() external returns (uint256 amount);
-    }
-}
-
-// ERC20 - DATA token
-sol! {
-    #[sol(rpc)]
-    interface IERC20 {
-        function balanceOf(address account) external view returns (uint256);
-        function allowance(address owner, address spender) external view returns (uint256);
-        function approve(address spender, uint256 amount) external returns (bool);
-        function transfer(address to, uint256 amount) external returns (bool);
-    }
-}
+use crate::error::{GhostnetError, Result};
+use crate::state::{
+    ActiveBoost, ArcadeGame, BoostOffer, BoostType, CullingRisk, DeadPoolRound, ExitToll, Level,
+    ResetTimer,
+};
 
 // ═══════════════════════════════════════════════════════════════════════════════
 // CONTRACT WRAPPERS
@@ -105,6 +35,9 @@ pub struct GhostnetContracts {
 
     /// DATA token contract address.
     pub data_token: Address,
+
+    /// DeadPool contract address, if rounds are played.
+    pub dead_pool: Option<Address>,
 }
 
 impl GhostnetContracts {
@@ -116,6 +49,7 @@ impl GhostnetContracts {
             hash_crash: config.hash_crash,
             arcade_core: config.arcade_core,
             data_token: config.data_token,
+            dead_pool: config.dead_pool,
         }
     }
 }
@@ -132,7 +66,7 @@ impl GhostnetContracts {
     /// Build calldata for `jackIn(amount, level)`.
     #[must_use]
     pub fn encode_jack_in(&self, amount: U256, level: Level) -> Bytes {
-        let call = IGhostCore::jackInCall {
+        let call = ghost_core::jackInCall {
             amount,
             level: level.as_u8(),
         };
@@ -142,24 +76,61 @@ impl GhostnetContracts {
     /// Build calldata for `addStake(amount)`.
     #[must_use]
     pub fn encode_add_stake(&self, amount: U256) -> Bytes {
-        let call = IGhostCore::addStakeCall { amount };
+        let call = ghost_core::addStakeCall { amount };
         Bytes::from(call.abi_encode())
     }
 
-    /// Build calldata for `extract()`.
+    /// Build calldata for `extract()`, or `extract(amount)` to extract only
+    /// `amount` of the stake.
     #[must_use]
-    pub fn encode_extract(&self) -> Bytes {
-        let call = IGhostCore::extractCall {};
-        Bytes::from(call.abi_encode())
+    pub fn encode_extract(&self, amount: Option<U256>) -> Bytes {
+        let calldata = amount.map_or_else(
+            || ghost_core::extract_0Call {}.abi_encode(),
+            |amount| ghost_core::extract_1Call { amount }.abi_encode(),
+        );
+        Bytes::from(calldata)
     }
 
     /// Build calldata for `claimRewards()`.
     #[must_use]
     pub fn encode_claim_rewards(&self) -> Bytes {
-        let call = IGhostCore::claimRewardsCall {};
+        let call = ghost_core::claimRewardsCall {};
+        Bytes::from(call.abi_encode())
+    }
+
+    /// Build calldata for `applyBoost(...)` with a signed grant.
+    #[must_use]
+    pub fn encode_apply_boost(&self, offer: &BoostOffer) -> Bytes {
+        let call = ghost_core::applyBoostCall {
+            boostType: offer.boost_type.as_u8(),
+            valueBps: offer.value_bps,
+            expiry: offer.expiry,
+            nonce: offer.nonce,
+            signature: offer.signature.clone(),
+        };
+        Bytes::from(call.abi_encode())
+    }
+
+    /// Build calldata for `getActiveBoosts(user)`.
+    #[must_use]
+    pub fn encode_get_active_boosts(&self, user: Address) -> Bytes {
+        let call = ghost_core::getActiveBoostsCall { user };
         Bytes::from(call.abi_encode())
     }
 
+    /// Build calldata for `getSystemReset()`, the reset timer whose pending
+    /// penalty is the exit toll.
+    #[must_use]
+    pub fn encode_get_system_reset(&self) -> Bytes {
+        Bytes::from(ghost_core::getSystemResetCall {}.abi_encode())
+    }
+
+    /// Build calldata for `getCullingRisk(user)`.
+    #[must_use]
+    pub fn encode_get_culling_risk(&self, user: Address) -> Bytes {
+        Bytes::from(ghost_core::getCullingRiskCall { user }.abi_encode())
+    }
+
     // ─────────────────────────────────────────────────────────────────────────
     // HashCrash calldata
     // ─────────────────────────────────────────────────────────────────────────
@@ -167,21 +138,88 @@ impl GhostnetContracts {
     /// Build calldata for `placeBet(amount, targetMultiplier)`.
     #[must_use]
     pub fn encode_hashcrash_bet(&self, amount: U256, target_multiplier: u16) -> Bytes {
-        let call = IHashCrash::placeBetCall {
+        let call = hash_crash::placeBetCall {
             amount,
             targetMultiplier: U256::from(target_multiplier),
         };
         Bytes::from(call.abi_encode())
     }
 
+    /// Build calldata for `getRound(roundId)`.
+    #[must_use]
+    pub fn encode_get_round(&self, round_id: u64) -> Bytes {
+        let call = hash_crash::getRoundCall {
+            roundId: U256::from(round_id),
+        };
+        Bytes::from(call.abi_encode())
+    }
+
+    /// Build calldata for `getPlayerBet(roundId, player)`.
+    #[must_use]
+    pub fn encode_get_player_bet(&self, round_id: u64, player: Address) -> Bytes {
+        let call = hash_crash::getPlayerBetCall {
+            roundId: U256::from(round_id),
+            player,
+        };
+        Bytes::from(call.abi_encode())
+    }
+
     // ─────────────────────────────────────────────────────────────────────────
     // ArcadeCore calldata
     // ─────────────────────────────────────────────────────────────────────────
 
+    /// Build calldata for `play(gameId, amount)`.
+    #[must_use]
+    pub fn encode_arcade_play(&self, game_id: u64, amount: U256) -> Bytes {
+        let call = arcade_core::playCall {
+            gameId: U256::from(game_id),
+            amount,
+        };
+        Bytes::from(call.abi_encode())
+    }
+
+    /// Build calldata for `getGames()`.
+    #[must_use]
+    pub fn encode_get_games(&self) -> Bytes {
+        let call = arcade_core::getGamesCall {};
+        Bytes::from(call.abi_encode())
+    }
+
     /// Build calldata for `withdrawPayout()`.
     #[must_use]
     pub fn encode_withdraw_payout(&self) -> Bytes {
-        let call = IArcadeCore::withdrawPayoutCall {};
+        let call = arcade_core::withdrawPayoutCall {};
+        Bytes::from(call.abi_encode())
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────
+    // DeadPool calldata
+    // ─────────────────────────────────────────────────────────────────────────
+
+    /// Build calldata for DeadPool's `placeBet(roundId, isOver, amount)`.
+    #[must_use]
+    pub fn encode_dead_pool_bet(&self, round_id: u64, is_over: bool, amount: U256) -> Bytes {
+        let call = dead_pool::placeBetCall {
+            roundId: U256::from(round_id),
+            isOver: is_over,
+            amount,
+        };
+        Bytes::from(call.abi_encode())
+    }
+
+    /// Build calldata for DeadPool's `getRound(roundId)`.
+    #[must_use]
+    pub fn encode_get_dead_pool_round(&self, round_id: u64) -> Bytes {
+        let call = dead_pool::getRoundCall {
+            roundId: U256::from(round_id),
+        };
+        Bytes::from(call.abi_encode())
+    }
+
+    /// Build calldata for DeadPool's `roundCount()`.
+    #[must_use]
+    pub fn encode_round_count(&self) -> Bytes {
+        let call = dead_pool::roundCountCall {};
         Bytes::from(call.abi_encode())
     }
 
@@ -192,16 +230,253 @@ impl GhostnetContracts {
     /// Build calldata for `approve(spender, amount)`.
     #[must_use]
     pub fn encode_approve(&self, spender: Address, amount: U256) -> Bytes {
-        let call = IERC20::approveCall { spender, amount };
+        let call = data_token::approveCall {
+            spender,
+            value: amount,
+        };
+        Bytes::from(call.abi_encode())
+    }
+
+    /// Build calldata for `TAX_RATE_BPS()`.
+    #[must_use]
+    pub fn encode_tax_rate(&self) -> Bytes {
+        let call = data_token::TAX_RATE_BPSCall {};
+        Bytes::from(call.abi_encode())
+    }
+
+    /// Build calldata for `isExcludedFromTax(account)`.
+    #[must_use]
+    pub fn encode_is_excluded_from_tax(&self, account: Address) -> Bytes {
+        let call = data_token::isExcludedFromTaxCall { account };
         Bytes::from(call.abi_encode())
     }
 }
 
+// ═══════════════════════════════════════════════════════════════════════════════
+// VIEW RESULTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Decode the result of `getActiveBoosts(user)`.
+///
+/// Boosts of a type this crate does not know are skipped.
+///
+/// # Errors
+///
+/// Returns [`GhostnetError::ContractCall`] if the data is not a valid result.
+pub fn decode_active_boosts(data: &[u8]) -> Result<Vec<ActiveBoost>> {
+    let boosts = ghost_core::getActiveBoostsCall::abi_decode_returns(data).map_err(|e| {
+        GhostnetError::ContractCall(format!("malformed getActiveBoosts result: {e}"))
+    })?;
+    Ok(boosts
+        .into_iter()
+        .filter_map(|boost| {
+            Some(ActiveBoost {
+                boost_type: BoostType::from_u8(boost.boostType)?,
+                value_bps: boost.valueBps,
+                expiry: boost.expiry,
+            })
+        })
+        .collect())
+}
+
+/// Decode the exit toll from the result of `getSystemReset()`.
+///
+/// A system reset's penalty is taken from a position's stake the next time
+/// it is settled, extraction included.
+///
+/// # Errors
+///
+/// Returns [`GhostnetError::ContractCall`] if the data is not a valid result.
+pub fn decode_exit_toll(data: &[u8]) -> Result<ExitToll> {
+    decode_reset_timer(data).map(|timer| timer.exit_toll())
+}
+
+/// Decode the reset timer from the result of `getSystemReset()`.
+///
+/// # Errors
+///
+/// Returns [`GhostnetError::ContractCall`] if the data is not a valid result.
+pub fn decode_reset_timer(data: &[u8]) -> Result<ResetTimer> {
+    let reset = ghost_core::getSystemResetCall::abi_decode_returns(data).map_err(|e| {
+        GhostnetError::ContractCall(format!("malformed getSystemReset result: {e}"))
+    })?;
+    Ok(ResetTimer {
+        deadline: reset.deadline,
+        // Epochs only ever count resets
+        epoch: u64::try_from(reset.epoch).unwrap_or(u64::MAX),
+        penalty_bps: reset.penaltyBps,
+    })
+}
+
+/// Decode the result of `getCullingRisk(user)`.
+///
+/// # Errors
+///
+/// Returns [`GhostnetError::ContractCall`] if the data is not a valid result.
+pub fn decode_culling_risk(data: &[u8]) -> Result<CullingRisk> {
+    let risk = ghost_core::getCullingRiskCall::abi_decode_returns(data).map_err(|e| {
+        GhostnetError::ContractCall(format!("malformed getCullingRisk result: {e}"))
+    })?;
+    Ok(CullingRisk {
+        risk_bps: risk.riskBps,
+        eligible: risk.isEligible,
+        // Despite its name, GhostCore reports the capacity in basis points
+        capacity_bps: risk.capacityPct,
+    })
+}
+
+/// Decode the result of `getGames()`.
+///
+/// Paused games and games whose ID does not fit in a `u64` are skipped.
+///
+/// # Errors
+///
+/// Returns [`GhostnetError::ContractCall`] if the data is not a valid result,
+/// which includes the empty result of a call to an address without code.
+pub fn decode_arcade_games(data: &[u8]) -> Result<Vec<ArcadeGame>> {
+    let games = arcade_core::getGamesCall::abi_decode_returns(data)
+        .map_err(|e| GhostnetError::ContractCall(format!("malformed getGames result: {e}")))?;
+    Ok(games
+        .into_iter()
+        .filter(|game| !game.paused)
+        .filter_map(|game| {
+            Some(ArcadeGame {
+                game_id: u64::try_from(game.gameId).ok()?,
+                game: game.game,
+                min_entry: game.minEntry,
+                max_entry: (!game.maxEntry.is_zero()).then_some(game.maxEntry),
+                rake_bps: game.rakeBps,
+            })
+        })
+        .collect())
+}
+
+/// Scale of HashCrash multipliers (100 = 1.00x).
+pub const MULTIPLIER_PRECISION: u64 = 100;
+
+/// The parts of a HashCrash round that decide how its bets ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RoundView {
+    /// Session state (`IArcadeTypes.SessionState`).
+    pub state: u8,
+
+    /// Crash point in hundredths (250 = 2.50x), zero until revealed.
+    pub crash_multiplier: U256,
+}
+
+impl RoundView {
+    /// Session state of a cancelled round.
+    pub const CANCELLED: u8 = 6;
+
+    /// Session state of a round whose seed block expired.
+    pub const EXPIRED: u8 = 7;
+
+    /// Check if the round's bets were refunded instead of played out.
+    #[must_use]
+    pub const fn is_refunded(&self) -> bool {
+        matches!(self.state, Self::CANCELLED | Self::EXPIRED)
+    }
+
+    /// Check if the crash point has been revealed.
+    #[must_use]
+    pub fn is_revealed(&self) -> bool {
+        !self.crash_multiplier.is_zero()
+    }
+}
+
+/// Decode the result of `getRound(roundId)`.
+///
+/// # Errors
+///
+/// Returns [`GhostnetError::ContractCall`] if the data is not a valid result.
+pub fn decode_round(data: &[u8]) -> Result<RoundView> {
+    let round = hash_crash::getRoundCall::abi_decode_returns(data)
+        .map_err(|e| GhostnetError::ContractCall(format!("malformed getRound result: {e}")))?;
+    Ok(RoundView {
+        state: round.state,
+        crash_multiplier: round.crashMultiplier,
+    })
+}
+
+/// Decode the net (after rake) amount from the result of
+/// `getPlayerBet(roundId, player)`. Payouts are paid on this amount.
+///
+/// # Errors
+///
+/// Returns [`GhostnetError::ContractCall`] if the data is not a valid result.
+pub fn decode_player_bet_amount(data: &[u8]) -> Result<U256> {
+    hash_crash::getPlayerBetCall::abi_decode_returns(data)
+        .map(|bet| bet.amount)
+        .map_err(|e| GhostnetError::ContractCall(format!("malformed getPlayerBet result: {e}")))
+}
+
+/// Decode the result of DeadPool's `getRound(roundId)` for round
+/// `round_id`, or `None` if the round does not exist or was resolved.
+///
+/// The round's `opened_at` is left at 0, for the caller to fill in.
+///
+/// # Errors
+///
+/// Returns [`GhostnetError::ContractCall`] if the data is not a valid result.
+pub fn decode_dead_pool_round(round_id: u64, data: &[u8]) -> Result<Option<DeadPoolRound>> {
+    let round = dead_pool::getRoundCall::abi_decode_returns(data)
+        .map_err(|e| GhostnetError::ContractCall(format!("malformed getRound result: {e}")))?;
+    if round.deadline == 0 || round.resolved {
+        return Ok(None);
+    }
+    Ok(Some(DeadPoolRound {
+        round_id,
+        round_type: round.roundType,
+        line: round.line,
+        over_pool: round.overPool,
+        under_pool: round.underPool,
+        deadline: round.deadline,
+        opened_at: 0,
+    }))
+}
+
+/// Decode the result of DeadPool's `roundCount()`, the ID of its latest
+/// round.
+///
+/// # Errors
+///
+/// Returns [`GhostnetError::ContractCall`] if the data is not a valid result
+/// or the count does not fit in a `u64`.
+pub fn decode_round_count(data: &[u8]) -> Result<u64> {
+    let count = dead_pool::roundCountCall::abi_decode_returns(data)
+        .map_err(|e| GhostnetError::ContractCall(format!("malformed roundCount result: {e}")))?;
+    u64::try_from(count)
+        .map_err(|_| GhostnetError::ContractCall(format!("round count {count} out of range")))
+}
+
+/// Decode the result of `TAX_RATE_BPS()`, DataToken's transfer tax in basis
+/// points.
+///
+/// # Errors
+///
+/// Returns [`GhostnetError::ContractCall`] if the data is not a valid result.
+pub fn decode_tax_rate(data: &[u8]) -> Result<u16> {
+    data_token::TAX_RATE_BPSCall::abi_decode_returns(data)
+        .map_err(|e| GhostnetError::ContractCall(format!("malformed TAX_RATE_BPS result: {e}")))
+}
+
+/// Decode the result of `isExcludedFromTax(account)`.
+///
+/// # Errors
+///
+/// Returns [`GhostnetError::ContractCall`] if the data is not a valid result.
+pub fn decode_tax_exclusion(data: &[u8]) -> Result<bool> {
+    data_token::isExcludedFromTaxCall::abi_decode_returns(data).map_err(|e| {
+        GhostnetError::ContractCall(format!("malformed isExcludedFromTax result: {e}"))
+    })
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // TESTS
 // ═══════════════════════════════════════════════════════════════════════════════
 
 #[cfg(test)]
+#[allow(clippy::unwrap_used)]
 mod tests {
     use super::*;
 
@@ -211,6 +486,7 @@ mod tests {
             hash_crash: Address::repeat_byte(0x02),
             arcade_core: Address::repeat_byte(0x03),
             data_token: Address::repeat_byte(0x04),
+            dead_pool: Some(Address::repeat_byte(0x05)),
         }
     }
 
@@ -235,10 +511,15 @@ mod tests {
     #[test]
     fn encode_extract() {
         let contracts = test_contracts();
-        let calldata = contracts.encode_extract();
+        let calldata = contracts.encode_extract(None);
 
         // extract() has no args, just selector
         assert_eq!(calldata.len(), 4);
+
+        let partial = contracts.encode_extract(Some(U256::from(5)));
+        let call = ghost_core::extract_1Call::abi_decode(&partial).unwrap();
+        assert_eq!(call.amount, U256::from(5));
+        assert_ne!(partial[..4], calldata[..]);
     }
 
     #[test]
@@ -249,6 +530,201 @@ mod tests {
         assert!(!calldata.is_empty());
     }
 
+    #[test]
+    fn boost_calldata_roundtrip() {
+        let contracts = test_contracts();
+        let offer = BoostOffer {
+            boost_type: BoostType::YieldMultiplier,
+            value_bps: 1500,
+            expiry: 1_700_000_000,
+            nonce: alloy::primitives::B256::repeat_byte(0x11),
+            signature: Bytes::from(vec![0xAB; 65]),
+            cost: U256::ZERO,
+        };
+        let call =
+            ghost_core::applyBoostCall::abi_decode(&contracts.encode_apply_boost(&offer)).unwrap();
+        assert_eq!(call.boostType, 1);
+        assert_eq!(call.valueBps, 1500);
+        assert_eq!(call.nonce, offer.nonce);
+
+        let boosts =
+            [(0, 500, 10), (7, 100, 20), (1, 1500, 30)].map(|(t, v, e)| ghost_core::Boost {
+                boostType: t,
+                valueBps: v,
+                expiry: e,
+            },
+        );
+        let data = ghost_core::getActiveBoostsCall::abi_encode_returns(&boosts.to_vec());
+        let decoded = decode_active_boosts(&data).unwrap();
+        assert_eq!(decoded.len(), 2, "unknown boost types are skipped");
+        assert_eq!(decoded[1], ActiveBoost { expiry: 30, ..offer.boost() });
+    }
+
+    #[test]
+    fn decode_exit_toll_from_system_reset() {
+        let contracts = test_contracts();
+        assert!(ghost_core::getSystemResetCall::abi_decode(&contracts.encode_get_system_reset())
+            .is_ok());
+
+        let reset = ghost_core::SystemReset {
+            deadline: 1_700_000_000,
+            lastDepositor: Address::repeat_byte(0xaa),
+            lastDepositTime: 1_699_990_000,
+            epoch: U256::from(2),
+            penaltyBps: 2500,
+        };
+        let data = ghost_core::getSystemResetCall::abi_encode_returns(&reset);
+        assert_eq!(decode_exit_toll(&data).unwrap(), ExitToll { penalty_bps: 2500 });
+        assert!(decode_exit_toll(&[]).is_err());
+        assert_eq!(
+            decode_reset_timer(&data).unwrap(),
+            ResetTimer {
+                deadline: 1_700_000_000,
+                epoch: 2,
+                penalty_bps: 2500,
+            }
+        );
+    }
+
+    #[test]
+    fn decode_transfer_tax_views() {
+        let contracts = test_contracts();
+        assert!(data_token::TAX_RATE_BPSCall::abi_decode(&contracts.encode_tax_rate()).is_ok());
+        let call = data_token::isExcludedFromTaxCall::abi_decode(
+            &contracts.encode_is_excluded_from_tax(Address::repeat_byte(0xaa)),
+        )
+        .unwrap();
+        assert_eq!(call.account, Address::repeat_byte(0xaa));
+
+        let data = data_token::TAX_RATE_BPSCall::abi_encode_returns(&1000);
+        assert_eq!(decode_tax_rate(&data).unwrap(), 1000);
+        assert!(decode_tax_rate(&[]).is_err());
+
+        let data = data_token::isExcludedFromTaxCall::abi_encode_returns(&true);
+        assert!(decode_tax_exclusion(&data).unwrap());
+        assert!(decode_tax_exclusion(&[]).is_err());
+    }
+
+    #[test]
+    fn decode_culling_risk_view() {
+        let contracts = test_contracts();
+        let call = ghost_core::getCullingRiskCall::abi_decode(
+            &contracts.encode_get_culling_risk(Address::repeat_byte(0xaa)),
+        )
+        .unwrap();
+        assert_eq!(call.user, Address::repeat_byte(0xaa));
+
+        let data = ghost_core::getCullingRiskCall::abi_encode_returns(
+            &ghost_core::getCullingRiskReturn {
+                riskBps: 7000,
+                isEligible: true,
+                capacityPct: 9500,
+            });
+        let risk = decode_culling_risk(&data).unwrap();
+        assert_eq!(
+            risk,
+            CullingRisk {
+                risk_bps: 7000,
+                eligible: true,
+                capacity_bps: 9500,
+            }
+        );
+        assert!(decode_culling_risk(&[]).is_err());
+    }
+
+    #[test]
+    fn decode_round_views() {
+        let round = |state: u8, crash: u64| {
+            hash_crash::getRoundCall::abi_encode_returns(&hash_crash::getRoundReturn {
+                state,
+                bettingEndTime: 0,
+                prizePool: U256::ZERO,
+                crashMultiplier: U256::from(crash),
+                totalPaidOut: U256::ZERO,
+                playerCount: U256::from(1),
+            })
+        };
+
+        let revealed = decode_round(&round(3, 250)).unwrap();
+        assert!(revealed.is_revealed() && !revealed.is_refunded());
+        let open = decode_round(&round(1, 0)).unwrap();
+        assert!(!open.is_revealed());
+        assert!(decode_round(&round(RoundView::CANCELLED, 0)).unwrap().is_refunded());
+        assert!(decode_round(&[0u8; 31]).is_err());
+    }
+
+    #[test]
+    fn arcade_calldata_roundtrip() {
+        let contracts = test_contracts();
+        let call =
+            arcade_core::playCall::abi_decode(&contracts.encode_arcade_play(7, U256::from(5)))
+                .unwrap();
+        assert_eq!(call.gameId, U256::from(7));
+        assert_eq!(call.amount, U256::from(5));
+
+        let listing = |id: u64, max: u64, paused: bool| arcade_core::GameListing {
+            gameId: U256::from(id),
+            game: Address::repeat_byte(0x10),
+            minEntry: U256::from(1),
+            maxEntry: U256::from(max),
+            rakeBps: 250,
+            paused,
+        };
+        let games = vec![listing(1, 100, false), listing(2, 0, false), listing(3, 100, true)];
+        let decoded =
+            decode_arcade_games(&arcade_core::getGamesCall::abi_encode_returns(&games)).unwrap();
+        assert_eq!(decoded.len(), 2, "paused games are skipped");
+        assert_eq!(decoded[0].max_entry, Some(U256::from(100)));
+        assert_eq!(decoded[1].max_entry, None, "zero means no limit");
+        assert!(decode_arcade_games(&[]).is_err());
+    }
+
+    #[test]
+    fn dead_pool_calldata_roundtrip() {
+        let contracts = test_contracts();
+        let calldata = contracts.encode_dead_pool_bet(3, true, U256::from(5));
+        let call = dead_pool::placeBetCall::abi_decode(&calldata).unwrap();
+        assert_eq!(call.roundId, U256::from(3));
+        assert!(call.isOver);
+        assert_eq!(call.amount, U256::from(5));
+
+        let round = |deadline: u64, resolved: bool| {
+            dead_pool::getRoundCall::abi_encode_returns(&dead_pool::Round {
+                roundType: 1,
+                targetLevel: 0,
+                line: U256::from(1),
+                overPool: U256::from(30),
+                underPool: U256::from(70),
+                deadline,
+                resolveTime: 0,
+                resolved,
+                outcome: false,
+            })
+        };
+        let open = decode_dead_pool_round(3, &round(1_000, false))
+            .unwrap()
+            .unwrap();
+        assert_eq!(open.round_id, 3);
+        assert_eq!(open.round_type, 1);
+        assert_eq!(
+            (open.over_pool, open.under_pool),
+            (U256::from(30), U256::from(70))
+        );
+        assert_eq!(
+            decode_dead_pool_round(3, &round(1_000, true)).unwrap(),
+            None
+        );
+        assert_eq!(
+            decode_dead_pool_round(3, &round(0, false)).unwrap(),
+            None,
+            "no such round"
+        );
+
+        let count = dead_pool::roundCountCall::abi_encode_returns(&U256::from(12));
+        assert_eq!(decode_round_count(&count).unwrap(), 12);
+        assert!(decode_round_count(&[]).is_err());
+    }
+
     #[test]
     fn encode_approve() {
         let contracts = test_contracts();
@@ -0,0 +1,363 @@
+//! GHOSTNET events in a wallet's own transaction receipts.
+//!
+//! The receipt of a GhostCore action already carries the event with the
+//! position's new values (`JackedIn`, `StakeAdded`, `Extracted`, ...), so the
+//! wallet's state can be updated from it instead of being read again. The
+//! events are decoded with the shared bindings from [`ghostnet_abi`].
+
+use alloy::primitives::{Address, U256};
+use alloy::rpc::types::Log;
+use alloy::sol_types::SolEvent;
+use evm_provider::TransactionReceipt;
+use ghostnet_abi::data_token::Transfer;
+use ghostnet_abi::ghost_core::{BoostApplied, Extracted, JackedIn, PositionCulled, StakeAdded};
+
+use crate::state::{ActiveBoost, BoostType, GhostnetState, Level, Position};
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// EVENTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// A GhostCore event that changes a single position.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GhostnetEvent {
+    /// A position was entered.
+    JackedIn(JackedIn),
+    /// Stake was added to a position.
+    StakeAdded(StakeAdded),
+    /// A position was extracted.
+    Extracted(Extracted),
+    /// A position was culled to make room for another.
+    PositionCulled(PositionCulled),
+    /// A boost was applied to a position.
+    BoostApplied(BoostApplied),
+}
+
+impl GhostnetEvent {
+    /// Decode a log, if it is one of the events.
+    fn decode(log: &Log) -> Option<Self> {
+        let log = &log.inner;
+        let event = match log.topics().first().copied()? {
+            JackedIn::SIGNATURE_HASH => Self::JackedIn(JackedIn::decode_log(log).ok()?.data),
+            StakeAdded::SIGNATURE_HASH => Self::StakeAdded(StakeAdded::decode_log(log).ok()?.data),
+            Extracted::SIGNATURE_HASH => Self::Extracted(Extracted::decode_log(log).ok()?.data),
+            PositionCulled::SIGNATURE_HASH => {
+                Self::PositionCulled(PositionCulled::decode_log(log).ok()?.data)
+            }
+            BoostApplied::SIGNATURE_HASH => {
+                Self::BoostApplied(BoostApplied::decode_log(log).ok()?.data)
+            }
+            _ => return None,
+        };
+        Some(event)
+    }
+
+    /// The user whose position the event changes.
+    #[must_use]
+    pub const fn user(&self) -> Address {
+        match self {
+            Self::JackedIn(e) => e.user,
+            Self::StakeAdded(e) => e.user,
+            Self::Extracted(e) => e.user,
+            Self::PositionCulled(e) => e.victim,
+            Self::BoostApplied(e) => e.user,
+        }
+    }
+}
+
+/// Parse the GhostCore position events out of a receipt.
+///
+/// Only logs emitted by `ghost_core` are decoded; unrelated logs (token
+/// transfers, other contracts) are skipped, as is everything in a reverted
+/// receipt.
+#[must_use]
+pub fn parse_ghostnet_events(
+    receipt: &TransactionReceipt,
+    ghost_core: Address,
+) -> Vec<GhostnetEvent> {
+    if !receipt.success {
+        return Vec::new();
+    }
+    receipt
+        .logs
+        .iter()
+        .filter(|log| log.address() == ghost_core)
+        .filter_map(GhostnetEvent::decode)
+        .collect()
+}
+
+/// DATA transferred to `player` in a receipt, as the `Transfer` logs of
+/// `data_token` show; nothing for a reverted receipt.
+#[must_use]
+pub fn data_received(receipt: &TransactionReceipt, data_token: Address, player: Address) -> U256 {
+    if !receipt.success {
+        return U256::ZERO;
+    }
+    receipt
+        .logs
+        .iter()
+        .filter(|log| log.address() == data_token)
+        .filter_map(|log| Some(Transfer::decode_log(&log.inner).ok()?.data))
+        .filter(|transfer| transfer.to == player)
+        .fold(U256::ZERO, |total, transfer| {
+            total.saturating_add(transfer.value)
+        })
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// STATE UPDATES
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Apply the events of `player`'s position to its state at `now_unix`.
+///
+/// Returns `false` if none of the events concern `player`, in which case the
+/// state is left as is and should be read from chain instead.
+pub fn apply_events(
+    state: &mut GhostnetState,
+    player: Address,
+    events: &[GhostnetEvent],
+    now_unix: u64,
+) -> bool {
+    let mut applied = false;
+    for event in events.iter().filter(|event| event.user() == player) {
+        applied = true;
+        match event {
+            GhostnetEvent::JackedIn(e) => {
+                let Some(level) = Level::from_u8(e.level) else {
+                    continue;
+                };
+                state.position = Some(Position {
+                    amount: e.newTotal,
+                    level,
+                    entry_timestamp: now_unix,
+                    last_add_timestamp: now_unix,
+                    alive: true,
+                    ghost_streak: 0,
+                    pending_rewards: U256::ZERO,
+                    effective_death_rate_bps: level.base_death_rate_bps(),
+                    // A new position starts out locked
+                    in_lock_period: true,
+                    active_boosts: Vec::new(),
+                });
+            }
+            GhostnetEvent::StakeAdded(e) => {
+                if let Some(position) = state.position.as_mut() {
+                    position.amount = e.newTotal;
+                    position.last_add_timestamp = now_unix;
+                }
+            }
+            GhostnetEvent::Extracted(_) | GhostnetEvent::PositionCulled(_) => {
+                state.position = None;
+            }
+            GhostnetEvent::BoostApplied(e) => {
+                if let (Some(position), Some(boost_type)) =
+                    (state.position.as_mut(), BoostType::from_u8(e.boostType))
+                {
+                    let boost = ActiveBoost {
+                        boost_type,
+                        value_bps: e.valueBps,
+                        expiry: e.expiry,
+                    };
+                    if !position.active_boosts.contains(&boost) {
+                        position.active_boosts.push(boost);
+                    }
+                }
+            }
+        }
+    }
+    applied
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use alloy::primitives::{B256, Bytes, LogData, TxHash};
+    use alloy::sol_types::SolEvent;
+
+    const GHOST_CORE: Address = Address::repeat_byte(0x11);
+    const PLAYER: Address = Address::repeat_byte(0xaa);
+    const OTHER: Address = Address::repeat_byte(0xbb);
+    const DATA: u128 = 1_000_000_000_000_000_000;
+
+    fn log(address: Address, data: LogData) -> Log {
+        Log {
+            inner: alloy::primitives::Log { address, data },
+            ..Log::default()
+        }
+    }
+
+    fn event_log(event: &impl SolEvent) -> Log {
+        log(GHOST_CORE, event.encode_log_data())
+    }
+
+    fn receipt(logs: Vec<Log>) -> TransactionReceipt {
+        TransactionReceipt {
+            tx_hash: TxHash::repeat_byte(1),
+            block_hash: B256::ZERO,
+            block_number: 42,
+            tx_index: 0,
+            from: PLAYER,
+            to: Some(GHOST_CORE),
+            contract_address: None,
+            gas_used: 100_000,
+            success: true,
+            logs,
+        }
+    }
+
+    fn jacked_in(amount: u128) -> JackedIn {
+        JackedIn {
+            user: PLAYER,
+            amount: U256::from(amount),
+            level: Level::Subnet.as_u8(),
+            newTotal: U256::from(amount),
+        }
+    }
+
+    fn state_after(events: &[GhostnetEvent], state: &mut GhostnetState) -> bool {
+        apply_events(state, PLAYER, events, 1_000)
+    }
+
+    #[test]
+    fn parses_each_event_type() {
+        let boost = BoostApplied {
+            user: PLAYER,
+            boostType: BoostType::DeathReduction.as_u8(),
+            valueBps: 3500,
+            expiry: 2_000,
+        };
+        let stake = StakeAdded {
+            user: PLAYER,
+            amount: U256::from(5 * DATA),
+            newTotal: U256::from(15 * DATA),
+        };
+        let extracted = Extracted {
+            user: PLAYER,
+            amount: U256::from(15 * DATA),
+            rewards: U256::from(DATA),
+        };
+        let culled = PositionCulled {
+            victim: PLAYER,
+            penaltyAmount: U256::from(DATA),
+            returnedAmount: U256::from(9 * DATA),
+            newEntrant: OTHER,
+        };
+        let receipt = receipt(vec![
+            event_log(&jacked_in(10 * DATA)),
+            event_log(&boost),
+            event_log(&stake),
+            event_log(&extracted),
+            event_log(&culled),
+        ]);
+
+        let events = parse_ghostnet_events(&receipt, GHOST_CORE);
+        assert_eq!(
+            events,
+            [
+                GhostnetEvent::JackedIn(jacked_in(10 * DATA)),
+                GhostnetEvent::BoostApplied(boost),
+                GhostnetEvent::StakeAdded(stake),
+                GhostnetEvent::Extracted(extracted),
+                GhostnetEvent::PositionCulled(culled),
+            ]
+        );
+    }
+
+    #[test]
+    fn unrelated_logs_yield_nothing() {
+        // An ERC20 transfer, and a JackedIn from another contract
+        let transfer = log(
+            Address::repeat_byte(0x22),
+            LogData::new_unchecked(
+                vec![
+                    alloy::primitives::keccak256("Transfer(address,address,uint256)"),
+                    PLAYER.into_word(),
+                    GHOST_CORE.into_word(),
+                ],
+                Bytes::from(U256::from(DATA).to_be_bytes_vec()),
+            ),
+        );
+        let elsewhere = log(OTHER, jacked_in(DATA).encode_log_data());
+        let receipt = receipt(vec![transfer, elsewhere]);
+
+        assert!(parse_ghostnet_events(&receipt, GHOST_CORE).is_empty());
+
+        let mut state = GhostnetState::default();
+        assert!(!state_after(&[], &mut state));
+    }
+
+    #[test]
+    fn reverted_receipts_yield_nothing() {
+        let mut receipt = receipt(vec![event_log(&jacked_in(DATA))]);
+        receipt.success = false;
+        assert!(parse_ghostnet_events(&receipt, GHOST_CORE).is_empty());
+    }
+
+    #[test]
+    fn events_update_the_position() {
+        let mut state = GhostnetState::default();
+        assert!(state_after(
+            &[GhostnetEvent::JackedIn(jacked_in(10 * DATA))],
+            &mut state
+        ));
+        let position = state.position.clone().unwrap();
+        assert_eq!(position.amount, U256::from(10 * DATA));
+        assert_eq!(position.level, Level::Subnet);
+        assert!(position.alive && position.in_lock_period);
+        assert_eq!(
+            (position.ghost_streak, position.entry_timestamp),
+            (0, 1_000)
+        );
+
+        let added = GhostnetEvent::StakeAdded(StakeAdded {
+            user: PLAYER,
+            amount: U256::from(5 * DATA),
+            newTotal: U256::from(15 * DATA),
+        });
+        let boost = GhostnetEvent::BoostApplied(BoostApplied {
+            user: PLAYER,
+            boostType: BoostType::YieldMultiplier.as_u8(),
+            valueBps: 2000,
+            expiry: 5_000,
+        });
+        assert!(state_after(&[added, boost], &mut state));
+        let position = state.position.clone().unwrap();
+        assert_eq!(position.amount, U256::from(15 * DATA));
+        assert_eq!(position.active_boosts.len(), 1);
+
+        let extracted = GhostnetEvent::Extracted(Extracted {
+            user: PLAYER,
+            amount: U256::from(15 * DATA),
+            rewards: U256::ZERO,
+        });
+        assert!(state_after(&[extracted], &mut state));
+        assert!(state.position.is_none());
+    }
+
+    #[test]
+    fn events_of_other_users_are_ignored() {
+        let mut state = GhostnetState::default();
+        let other = GhostnetEvent::JackedIn(JackedIn {
+            user: OTHER,
+            ..jacked_in(DATA)
+        });
+        assert!(!state_after(&[other], &mut state));
+        assert!(state.position.is_none());
+
+        // Culling someone else leaves the player's position alone
+        state_after(&[GhostnetEvent::JackedIn(jacked_in(DATA))], &mut state);
+        let culled = GhostnetEvent::PositionCulled(PositionCulled {
+            victim: OTHER,
+            penaltyAmount: U256::ZERO,
+            returnedAmount: U256::ZERO,
+            newEntrant: PLAYER,
+        });
+        assert!(!state_after(&[culled], &mut state));
+        assert!(state.position.is_some());
+    }
+}
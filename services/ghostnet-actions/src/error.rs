@@ -1,6 +1,7 @@
 //! Error types for the GHOSTNET actions plugin.
 
 use alloy::primitives::Address;
+use fleet_core::{ErrorClass, FleetError};
 use thiserror::Error;
 
 /// Result type alias for GHOSTNET operations.
@@ -89,6 +90,15 @@ pub enum GhostnetError {
     #[error("invalid level: {0}")]
     InvalidLevel(u8),
 
+    /// Wallet state that cannot be read as [`GhostnetState`](crate::GhostnetState).
+    #[error("invalid GHOSTNET state at {field}: {message}")]
+    InvalidState {
+        /// Path of the offending field, e.g. `position.amount`.
+        field: String,
+        /// What is wrong with it.
+        message: String,
+    },
+
     // ─────────────────────────────────────────────────────────────────────────
     // Contract/Provider errors
     // ─────────────────────────────────────────────────────────────────────────
@@ -96,17 +106,30 @@ pub enum GhostnetError {
     #[error("contract call failed: {0}")]
     ContractCall(String),
 
+    /// Contract call reverted with a decoded reason.
+    #[error("contract reverted: {0}")]
+    Reverted(String),
+
     /// Transaction failed.
     #[error("transaction failed: {0}")]
     TransactionFailed(String),
 
+    /// A transaction was estimated to need more gas than its action may use.
+    #[error("estimated gas {estimate} exceeds the cap of {max}")]
+    GasCapExceeded {
+        /// Estimated gas.
+        estimate: u64,
+        /// Most gas the action may use.
+        max: u64,
+    },
+
     /// Provider error.
     #[error("provider error: {0}")]
     Provider(#[from] evm_provider::ProviderError),
 
     /// Fleet core error.
     #[error("fleet error: {0}")]
-    Fleet(#[from] fleet_core::FleetError),
+    Fleet(#[from] FleetError),
 
     /// Serialization error.
     #[error("serialization error: {0}")]
@@ -114,13 +137,40 @@ pub enum GhostnetError {
 }
 
 impl GhostnetError {
+    /// How the fleet engine should react to this error.
+    ///
+    /// Failed calls and transactions are transient, decoded reverts and
+    /// violated preconditions are permanent, and provider errors are classified
+    /// by their cause (timeouts are transient, HTTP 429s rate limited).
+    #[must_use]
+    pub fn class(&self) -> ErrorClass {
+        match self {
+            Self::ContractCall(_) | Self::TransactionFailed(_) => ErrorClass::Transient,
+            Self::Provider(e) => e.into(),
+            Self::Fleet(e) => e.class(),
+            Self::InvalidConfig(_) => ErrorClass::Configuration,
+            Self::NoPosition(_)
+            | Self::PositionDead(_)
+            | Self::PositionExists(_)
+            | Self::PositionLocked(_)
+            | Self::InsufficientData { .. }
+            | Self::InsufficientGas { .. }
+            | Self::RoundNotBetting(_)
+            | Self::BetAmountOutOfRange { .. }
+            | Self::TargetMultiplierOutOfRange { .. }
+            | Self::InvalidActionData(_)
+            | Self::InvalidLevel(_)
+            | Self::InvalidState { .. }
+            | Self::Reverted(_)
+            | Self::GasCapExceeded { .. }
+            | Self::Serialization(_) => ErrorClass::Permanent,
+        }
+    }
+
     /// Returns true if this error is transient and the operation may succeed on retry.
     #[must_use]
-    pub const fn is_transient(&self) -> bool {
-        matches!(
-            self,
-            Self::Provider(_) | Self::ContractCall(_) | Self::TransactionFailed(_)
-        )
+    pub fn is_transient(&self) -> bool {
+        self.class().is_retryable()
     }
 
     /// Returns true if this error indicates insufficient balance.
@@ -130,6 +180,15 @@ impl GhostnetError {
     }
 }
 
+impl From<GhostnetError> for FleetError {
+    fn from(error: GhostnetError) -> Self {
+        match error {
+            GhostnetError::Fleet(e) => e,
+            e => Self::plugin(e.class(), e.to_string()),
+        }
+    }
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // TESTS
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -138,6 +197,7 @@ impl GhostnetError {
 mod tests {
     use super::*;
     use alloy::primitives::{Address, U256};
+    use evm_provider::ProviderError;
 
     #[test]
     fn transient_errors() {
@@ -146,6 +206,29 @@ mod tests {
         assert!(!GhostnetError::NoPosition(Address::ZERO).is_transient());
     }
 
+    #[test]
+    fn errors_are_classified() {
+        let timeout = ProviderError::Timeout(std::time::Duration::from_secs(5));
+        assert_eq!(GhostnetError::from(timeout).class(), ErrorClass::Transient);
+        let throttled = ProviderError::RateLimited("HTTP 429".into());
+        assert_eq!(GhostnetError::from(throttled).class(), ErrorClass::RateLimited);
+        let reverted = GhostnetError::Reverted("PositionAlreadyExists()".into());
+        assert_eq!(reverted.class(), ErrorClass::Permanent);
+        let config = GhostnetError::InvalidConfig("no ghost_core".into());
+        assert_eq!(config.class(), ErrorClass::Configuration);
+    }
+
+    #[test]
+    fn converts_to_fleet_error_with_class() {
+        let error = FleetError::from(GhostnetError::Reverted("Locked()".into()));
+        assert_eq!(error.class(), ErrorClass::Permanent);
+        assert!(error.to_string().contains("contract reverted: Locked()"));
+
+        // Wrapped fleet errors are unwrapped rather than nested
+        let error = FleetError::from(GhostnetError::Fleet(FleetError::GlobalPause));
+        assert!(matches!(error, FleetError::GlobalPause));
+    }
+
     #[test]
     fn insufficient_balance_errors() {
         assert!(GhostnetError::InsufficientData {
@@ -16,6 +16,8 @@
 //! │  └─ GhostnetPlugin: implements ActionPlugin                  │
 //! │  └─ GhostCore actions: jackIn, addStake, extract             │
 //! │  └─ HashCrash actions: placeBet (arcade game)                │
+//! │  └─ ArcadeCore actions: play (other arcade games)            │
+//! │  └─ DeadPool actions: placeBet (prediction rounds)           │
 //! └──────────────────────────────────┬───────────────────────────┘
 //!                                    │
 //!                                    ▼
@@ -43,6 +45,7 @@
 //! | `ghostnet.add_stake` | Add stake to existing position |
 //! | `ghostnet.extract` | Exit position and claim rewards |
 //! | `ghostnet.claim_rewards` | Claim pending rewards without exiting |
+//! | `ghostnet.apply_boost` | Apply a signed boost grant to the position |
 //!
 //! ## HashCrash (Arcade Game)
 //!
@@ -50,6 +53,18 @@
 //! |--------|-------------|
 //! | `ghostnet.hashcrash_bet` | Place a bet in the current round |
 //!
+//! ## ArcadeCore (Other Arcade Games)
+//!
+//! | Action | Description |
+//! |--------|-------------|
+//! | `ghostnet.arcade_play` | Enter a registered game, picked by risk tolerance |
+//!
+//! ## DeadPool (Prediction Rounds)
+//!
+//! | Action | Description |
+//! |--------|-------------|
+//! | `ghostnet.dead_pool_bet` | Bet on the side of an open round with the best expected value |
+//!
 //! # Configuration
 //!
 //! The plugin requires a [`GhostnetConfig`] with contract addresses:
@@ -77,7 +92,7 @@
 //!
 //! // Register with the fleet
 //! let mut registry = PluginRegistry::new();
-//! registry.register(Arc::new(plugin));
+//! registry.register(Arc::new(plugin))?;
 //! ```
 
 #![doc(html_root_url = "https://docs.ghostnet.io/ghostnet-actions")]
@@ -101,8 +116,12 @@ mod math;
 
 pub use config::GhostnetConfig;
 pub use error::{GhostnetError, Result};
-pub use plugin::GhostnetPlugin;
-pub use state::{GhostnetState, Level, Position};
+pub use math::{DeathRateTable, PositionValuation, RiskModel};
+pub use plugin::{GhostnetPlugin, PLUGIN_ID};
+pub use state::{
+    ActiveBoost, ArcadeGame, BetOutcome, BetRecord, BoostOffer, BoostType, CullingRisk,
+    DeadPoolRound, ExitToll, GhostnetState, Level, PnlLedger, Position, ResetTimer, TransferTax,
+};
 
 // ═══════════════════════════════════════════════════════════════════════════════
 // CRATE INFO
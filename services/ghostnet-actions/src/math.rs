@@ -18,9 +18,37 @@
 //! When cast to `f64` (53-bit mantissa), values above ~9 quadrillion
 //! (~9 million tokens with 18 decimals) lose precision. Basis point
 //! arithmetic avoids this entirely.
+//!
+//! # Exit Tolls
+//!
+//! [`net_extract_amount`] takes a basis-point toll off an amount as GhostCore
+//! does, rounding the toll up so the net amount is never overstated.
+//!
+//! # Transfer Tax
+//!
+//! DataToken taxes transfers between addresses not excluded from its tax,
+//! so less DATA lands than is sent. [`net_for_gross`] works out what lands
+//! as DataToken does, [`gross_for_net`] the least to send for an amount to
+//! land.
+//!
+//! # Amount Sampling
+//!
+//! Stake and bet sizes are drawn by [`sample_stake`] and [`sample_bet`] from
+//! profile-shaped log-normal distributions, see the `sampling` module.
+//!
+//! # Position Valuation
+//!
+//! [`PositionValuation`] weighs extracting a GhostCore position now against
+//! holding it through its next scan, see the `valuation` module.
 
 use alloy::primitives::U256;
 
+mod sampling;
+mod valuation;
+
+pub use sampling::{AmountBounds, AmountDistribution, sample_after_tax, sample_bet, sample_stake};
+pub use valuation::{DeathRateTable, PositionValuation, RiskModel};
+
 /// Basis points representing 100%.
 pub const BPS_100_PERCENT: u64 = 10_000;
 
@@ -59,6 +87,97 @@ pub fn percentage_of(amount: U256, bps: u64) -> U256 {
     amount * U256::from(bps) / U256::from(BPS_100_PERCENT)
 }
 
+/// What is left of `gross` after a toll of `toll_bps`.
+///
+/// The toll is rounded up, so the result is a lower bound of what arrives.
+/// Tolls above 100% take everything. Exact for any U256 amount: the amount
+/// is split at 10000 so the multiplication cannot overflow.
+///
+/// # Example
+///
+/// ```ignore
+/// use ghostnet_actions::math::net_extract_amount;
+/// use alloy::primitives::U256;
+///
+/// // A 2.5% toll on 1001 wei is 25.025 wei, taken as 26
+/// assert_eq!(net_extract_amount(U256::from(1001), 250), U256::from(975));
+/// ```
+#[must_use]
+pub fn net_extract_amount(gross: U256, toll_bps: u64) -> U256 {
+    let bps = U256::from(toll_bps.min(BPS_100_PERCENT));
+    let scale = U256::from(BPS_100_PERCENT);
+    let (whole, rest) = gross.div_rem(scale);
+    let toll = whole * bps + (rest * bps).div_ceil(scale);
+    gross - toll
+}
+
+/// What lands of `gross` sent under a transfer tax of `tax_bps`.
+///
+/// Rounds the tax down as DataToken does, exactly for any U256 amount like
+/// [`net_extract_amount`]. Taxes above 100% take everything.
+///
+/// # Example
+///
+/// ```ignore
+/// use ghostnet_actions::math::net_for_gross;
+/// use alloy::primitives::U256;
+///
+/// // A 10% tax on 1009 wei is 100.9 wei, taken as 100
+/// assert_eq!(net_for_gross(U256::from(1009), 1000), U256::from(909));
+/// ```
+#[must_use]
+pub fn net_for_gross(gross: U256, tax_bps: u64) -> U256 {
+    let bps = U256::from(tax_bps.min(BPS_100_PERCENT));
+    let scale = U256::from(BPS_100_PERCENT);
+    let (whole, rest) = gross.div_rem(scale);
+    let tax = whole * bps + rest * bps / scale;
+    gross - tax
+}
+
+/// The least to send under a transfer tax of `tax_bps` for `net` to land.
+///
+/// The inverse of [`net_for_gross`]: `net_for_gross(gross, tax_bps)` is
+/// exactly `net`, and one wei less would land less. Returns `None` if no
+/// U256 amount is enough, i.e. the tax is 100% or more, or the amount would
+/// overflow.
+///
+/// # Example
+///
+/// ```ignore
+/// use ghostnet_actions::math::gross_for_net;
+/// use alloy::primitives::U256;
+///
+/// // 1010 wei would land 909 as well, 1009 is the least
+/// assert_eq!(gross_for_net(U256::from(909), 1000), Some(U256::from(1009)));
+/// ```
+#[must_use]
+pub fn gross_for_net(net: U256, tax_bps: u64) -> Option<U256> {
+    if tax_bps == 0 || net.is_zero() {
+        return Some(net);
+    }
+    if tax_bps >= BPS_100_PERCENT {
+        return None;
+    }
+
+    // The tax is the least t with floor((net + t) * bps / scale) <= t, i.e.
+    // t = floor((net * bps - scale) / kept) + 1, clamped at zero. With
+    // net = q * kept + r that is q * bps plus a small term of r, so nothing
+    // but q * bps can overflow.
+    let scale = i128::from(BPS_100_PERCENT);
+    let bps = i128::from(tax_bps);
+    let kept = scale - bps;
+    let (q, r) = net.div_rem(U256::from(kept.unsigned_abs()));
+    let r = i128::try_from(r).ok()?;
+    let small = (r * bps - scale).div_euclid(kept) + 1;
+    let tax = q.checked_mul(U256::from(tax_bps))?;
+    let tax = if small >= 0 {
+        tax.checked_add(U256::from(small.unsigned_abs()))?
+    } else {
+        tax.saturating_sub(U256::from(small.unsigned_abs()))
+    };
+    net.checked_add(tax)
+}
+
 /// Calculate basis points from a floating-point percentage.
 ///
 /// Converts a 0.0-1.0 range to 0-10000 basis points.
@@ -99,34 +218,12 @@ pub fn random_bps(min_pct: f64, max_pct: f64, rng: &mut (impl rand::Rng + ?Sized
     rng.random_range(min_bps..=max_bps)
 }
 
-/// Apply jitter to a basis points value.
-///
-/// Multiplies the base value by a random factor in the given range.
-///
-/// # Arguments
-///
-/// * `base_bps` - Base basis points value
-/// * `jitter_min` - Minimum multiplier (e.g., 0.8 for -20%)
-/// * `jitter_max` - Maximum multiplier (e.g., 1.2 for +20%)
-/// * `rng` - Random number generator
-///
-/// # Returns
-///
-/// Jittered basis points, clamped to 0-10000
-#[must_use]
-#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss, clippy::cast_precision_loss)]
-pub fn apply_jitter(base_bps: u64, jitter_min: f64, jitter_max: f64, rng: &mut (impl rand::Rng + ?Sized)) -> u64 {
-    // Precision loss acceptable: base_bps is max 10000, fits in f64 mantissa
-    let jitter: f64 = rng.random_range(jitter_min..=jitter_max);
-    let result = (base_bps as f64 * jitter) as u64;
-    result.min(BPS_100_PERCENT)
-}
-
 // ═══════════════════════════════════════════════════════════════════════════════
 // TESTS
 // ═══════════════════════════════════════════════════════════════════════════════
 
 #[cfg(test)]
+#[allow(clippy::unwrap_used)]
 mod tests {
     use super::*;
 
@@ -170,6 +267,77 @@ mod tests {
         assert_eq!(half, expected);
     }
 
+    #[test]
+    fn net_extract_amount_rounds_the_toll_up() {
+        // 2.5% of 1000 is exact, of 1001 it is 25.025
+        assert_eq!(net_extract_amount(U256::from(1000), 250), U256::from(975));
+        assert_eq!(net_extract_amount(U256::from(1001), 250), U256::from(975));
+        // Any toll on a single wei takes it
+        assert_eq!(net_extract_amount(U256::from(1), 1), U256::ZERO);
+
+        // No toll, a full toll and more
+        assert_eq!(net_extract_amount(U256::from(1001), 0), U256::from(1001));
+        assert_eq!(net_extract_amount(U256::from(1001), 10_000), U256::ZERO);
+        assert_eq!(net_extract_amount(U256::from(1001), u64::MAX), U256::ZERO);
+    }
+
+    #[test]
+    fn net_extract_amount_is_exact_for_huge_amounts() {
+        // Multiplying first would overflow
+        let net = net_extract_amount(U256::MAX, 5000);
+        assert_eq!(net, U256::MAX / U256::from(2));
+        let toll = U256::MAX.div_ceil(U256::from(10_000));
+        assert_eq!(net_extract_amount(U256::MAX, 1), U256::MAX - toll);
+    }
+
+    #[test]
+    fn net_for_gross_rounds_the_tax_down() {
+        // 10% of 1000 is exact, of 1009 it is 100.9
+        assert_eq!(net_for_gross(U256::from(1000), 1000), U256::from(900));
+        assert_eq!(net_for_gross(U256::from(1009), 1000), U256::from(909));
+        // Too little for any tax
+        assert_eq!(net_for_gross(U256::from(9), 1000), U256::from(9));
+        assert_eq!(net_for_gross(U256::from(1), 1000), U256::from(1));
+
+        assert_eq!(net_for_gross(U256::from(1009), 0), U256::from(1009));
+        assert_eq!(net_for_gross(U256::from(1009), 10_000), U256::ZERO);
+        assert_eq!(net_for_gross(U256::from(1009), u64::MAX), U256::ZERO);
+    }
+
+    #[test]
+    fn gross_for_net_is_the_least_that_lands_net() {
+        for bps in [1, 250, 1000, 5000, 9999] {
+            for net in (0..2000).chain(10_000..10_100) {
+                let net = U256::from(net);
+                let gross = gross_for_net(net, bps).unwrap();
+                assert_eq!(net_for_gross(gross, bps), net, "{net} at {bps} bps");
+                assert!(
+                    gross.is_zero() || net_for_gross(gross - U256::from(1), bps) < net,
+                    "{gross} is not the least for {net} at {bps} bps"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn gross_for_net_round_trips_edge_amounts() {
+        let whale = U256::from(1_000_000_000_u128) * U256::from(10u128.pow(18));
+        for bps in [0, 1, 1000, 9999] {
+            // One wei, a net that is a whole multiple, and the largest nets
+            for net in [U256::from(1), U256::from(9000), whale, net_for_gross(U256::MAX, bps)] {
+                let gross = gross_for_net(net, bps).unwrap();
+                assert_eq!(net_for_gross(gross, bps), net, "{net} at {bps} bps");
+            }
+        }
+
+        // A single wei needs no more than itself below 100% tax
+        assert_eq!(gross_for_net(U256::from(1), 1000), Some(U256::from(1)));
+        // Beyond the largest net, and under a full tax, nothing is enough
+        assert_eq!(gross_for_net(U256::MAX, 1000), None);
+        assert_eq!(gross_for_net(U256::from(1), 10_000), None);
+        assert_eq!(gross_for_net(U256::ZERO, 10_000), Some(U256::ZERO));
+    }
+
     #[test]
     fn pct_to_bps_conversion() {
         assert_eq!(pct_to_bps(0.0), 0);
@@ -193,16 +361,4 @@ mod tests {
             assert!(bps <= 5000, "bps {bps} should be <= 5000 (50%)");
         }
     }
-
-    #[test]
-    fn apply_jitter_stays_bounded() {
-        use rand::SeedableRng;
-        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
-        
-        // Even with high jitter, should never exceed 100%
-        for _ in 0..100 {
-            let jittered = apply_jitter(8000, 0.8, 1.5, &mut rng);
-            assert!(jittered <= 10000, "jittered {jittered} should be <= 10000");
-        }
-    }
 }
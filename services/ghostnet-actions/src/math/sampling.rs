@@ -0,0 +1,425 @@
+//! Stake and bet amount sampling.
+//!
+//! Amounts that are always a fixed share of the balance give every wallet
+//! the same relative sizes. Instead, the share is drawn from a log-normal
+//! distribution shaped by the profile: patient profiles (whales) commit a
+//! larger median share with little spread, risk-seeking and impatient ones
+//! (degens) a smaller median share with a wide spread.
+//!
+//! Samples are clamped to the caller's [`AmountBounds`] and rounded down to
+//! two significant digits, or sometimes to a round figure like exactly 100
+//! DATA, the way people type amounts.
+//!
+//! Under a transfer tax, [`sample_after_tax`] samples what should land and
+//! sends enough for it to.
+
+use alloy::primitives::U256;
+use fleet_core::profiles::BehaviorProfile;
+use rand::Rng;
+
+use super::{gross_for_net, net_for_gross, pct_to_bps, percentage_of};
+
+/// Chance that a sample is rounded to a round figure (1, 2 or 5 followed by zeros).
+const ROUND_FIGURE_CHANCE: f64 = 0.15;
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// BOUNDS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Limits a sampled amount must stay within.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AmountBounds {
+    /// Smallest amount worth sending.
+    pub min: U256,
+    /// Largest amount allowed.
+    pub max: U256,
+    /// Balance kept in the wallet.
+    pub reserve: U256,
+}
+
+impl AmountBounds {
+    /// Create bounds without a reserve.
+    #[must_use]
+    pub const fn new(min: U256, max: U256) -> Self {
+        Self {
+            min,
+            max,
+            reserve: U256::ZERO,
+        }
+    }
+
+    /// Keep `reserve` of the balance.
+    #[must_use]
+    pub const fn with_reserve(mut self, reserve: U256) -> Self {
+        self.reserve = reserve;
+        self
+    }
+
+    /// Largest amount available from `balance`.
+    fn cap(&self, balance: U256) -> U256 {
+        balance.saturating_sub(self.reserve).min(self.max)
+    }
+}
+
+/// Sample an amount to send from `balance` within `bounds`, so that what
+/// lands after a transfer tax of `tax_bps` is what `sample` drew.
+///
+/// `sample` draws the landing amount from what can land of the balance
+/// after the reserve, within `bounds` with `max` lowered to what can land
+/// of it. The amount sent is the least that lands the draw, so it stays
+/// within `bounds` too. Returns zero if `sample` does, or nothing can land.
+/// Without a tax this is `sample(balance, bounds)`.
+pub fn sample_after_tax(
+    balance: U256,
+    bounds: &AmountBounds,
+    tax_bps: u64,
+    sample: impl FnOnce(U256, &AmountBounds) -> U256,
+) -> U256 {
+    if tax_bps == 0 {
+        return sample(balance, bounds);
+    }
+    let spendable = net_for_gross(balance.saturating_sub(bounds.reserve), tax_bps);
+    let landing = AmountBounds::new(bounds.min, net_for_gross(bounds.max, tax_bps));
+    gross_for_net(sample(spendable, &landing), tax_bps).unwrap_or(U256::ZERO)
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// DISTRIBUTION
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Log-normal distribution of the share of the balance to commit.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AmountDistribution {
+    /// Median share of the available balance (0.0 - 1.0).
+    pub median_share: f64,
+    /// Standard deviation of the share's logarithm.
+    pub sigma: f64,
+}
+
+impl AmountDistribution {
+    /// Distribution of stakes for `profile`.
+    ///
+    /// The median share grows with patience and shrinks with risk tolerance:
+    /// about 44% for a whale, 17% for a degen.
+    #[must_use]
+    pub fn stake(profile: &BehaviorProfile) -> Self {
+        Self {
+            median_share: (-0.1f64).mul_add(
+                profile.risk_tolerance,
+                0.35f64.mul_add(profile.patience, 0.15),
+            ),
+            sigma: Self::sigma(profile),
+        }
+    }
+
+    /// Distribution of bets for `profile`, betting at most about `max_share`
+    /// of the balance.
+    ///
+    /// The median share grows with risk tolerance.
+    #[must_use]
+    pub fn bet(profile: &BehaviorProfile, max_share: f64) -> Self {
+        Self {
+            median_share: max_share * profile.risk_tolerance * 0.6,
+            sigma: Self::sigma(profile),
+        }
+    }
+
+    /// Spread for `profile`, growing with risk tolerance and impatience.
+    fn sigma(profile: &BehaviorProfile) -> f64 {
+        0.5f64.mul_add(
+            profile.risk_tolerance,
+            0.25f64.mul_add(1.0 - profile.patience, 0.15),
+        )
+    }
+
+    /// The distribution with its median scaled by `factor`.
+    #[must_use]
+    pub fn scaled(self, factor: f64) -> Self {
+        Self {
+            median_share: self.median_share * factor,
+            ..self
+        }
+    }
+
+    /// Sample an amount from `balance` within `bounds`.
+    ///
+    /// Returns zero if the balance cannot cover `bounds.min` after the
+    /// reserve.
+    pub fn sample(
+        &self,
+        balance: U256,
+        bounds: &AmountBounds,
+        rng: &mut (impl Rng + ?Sized),
+    ) -> U256 {
+        let cap = bounds.cap(balance);
+        if cap.is_zero() || cap < bounds.min {
+            return U256::ZERO;
+        }
+
+        let share = self.median_share.max(0.0) * (self.sigma * standard_normal(rng)).exp();
+        let amount = percentage_of(balance.saturating_sub(bounds.reserve), pct_to_bps(share));
+        let amount = amount.clamp(bounds.min, cap);
+
+        let rounded = if rng.random_bool(ROUND_FIGURE_CHANCE) {
+            round_figure(amount)
+        } else {
+            two_significant_digits(amount)
+        };
+        // Rounding down keeps the amount within `cap`, only `min` can be crossed
+        rounded.max(bounds.min)
+    }
+}
+
+/// Sample a stake from `balance` for `profile`, see [`AmountDistribution::stake`].
+pub fn sample_stake(
+    profile: &BehaviorProfile,
+    balance: U256,
+    bounds: &AmountBounds,
+    rng: &mut (impl Rng + ?Sized),
+) -> U256 {
+    AmountDistribution::stake(profile).sample(balance, bounds, rng)
+}
+
+/// Sample a bet from `balance` for `profile`, see [`AmountDistribution::bet`].
+pub fn sample_bet(
+    profile: &BehaviorProfile,
+    balance: U256,
+    max_share: f64,
+    bounds: &AmountBounds,
+    rng: &mut (impl Rng + ?Sized),
+) -> U256 {
+    AmountDistribution::bet(profile, max_share).sample(balance, bounds, rng)
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// HELPERS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// A standard normal sample (Box-Muller transform).
+fn standard_normal(rng: &mut (impl Rng + ?Sized)) -> f64 {
+    // 1 - [0, 1) avoids ln(0)
+    let u1 = 1.0 - rng.random::<f64>();
+    let u2 = rng.random::<f64>();
+    (-2.0 * u1.ln()).sqrt() * (std::f64::consts::TAU * u2).cos()
+}
+
+/// The power of ten dividing `amount` down to its leading `digits` digits.
+fn digit_unit(amount: U256, digits: u32) -> U256 {
+    let ten = U256::from(10);
+    let limit = ten.pow(U256::from(digits));
+    let mut unit = U256::from(1);
+    while amount / unit >= limit {
+        unit *= ten;
+    }
+    unit
+}
+
+/// Round `amount` down to two significant digits.
+fn two_significant_digits(amount: U256) -> U256 {
+    let unit = digit_unit(amount, 2);
+    amount / unit * unit
+}
+
+/// Round `amount` down to the nearest 1, 2 or 5 followed by zeros.
+fn round_figure(amount: U256) -> U256 {
+    if amount.is_zero() {
+        return amount;
+    }
+    let unit = digit_unit(amount, 1);
+    let leading = match amount / unit {
+        n if n >= U256::from(5) => 5,
+        n if n >= U256::from(2) => 2,
+        _ => 1,
+    };
+    unit * U256::from(leading)
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    const DATA: u128 = 1_000_000_000_000_000_000;
+    const SAMPLES: usize = 10_000;
+
+    fn samples(
+        distribution: AmountDistribution,
+        balance: U256,
+        bounds: &AmountBounds,
+    ) -> Vec<U256> {
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut samples: Vec<U256> = (0..SAMPLES)
+            .map(|_| distribution.sample(balance, bounds, &mut rng))
+            .collect();
+        samples.sort();
+        samples
+    }
+
+    #[test]
+    fn samples_respect_bounds_and_reserve() {
+        let bounds = AmountBounds::new(U256::from(10 * DATA), U256::from(400 * DATA))
+            .with_reserve(U256::from(100 * DATA));
+        for profile in [
+            BehaviorProfile::whale(),
+            BehaviorProfile::degen(),
+            BehaviorProfile::sniper(),
+        ] {
+            for balance in [110, 150, 1_000, 100_000] {
+                let balance = U256::from(balance * DATA);
+                let cap = (balance - U256::from(100 * DATA)).min(U256::from(400 * DATA));
+                for amount in samples(AmountDistribution::stake(&profile), balance, &bounds) {
+                    assert!(
+                        amount >= bounds.min,
+                        "{} sampled {amount} below min",
+                        profile.name
+                    );
+                    assert!(
+                        amount <= cap,
+                        "{} sampled {amount} above {cap}",
+                        profile.name
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn nothing_sampled_below_min() {
+        let bounds =
+            AmountBounds::new(U256::from(10 * DATA), U256::MAX).with_reserve(U256::from(5 * DATA));
+        let mut rng = StdRng::seed_from_u64(1);
+        let amount = sample_stake(
+            &BehaviorProfile::whale(),
+            U256::from(14 * DATA),
+            &bounds,
+            &mut rng,
+        );
+        assert_eq!(amount, U256::ZERO);
+    }
+
+    #[test]
+    fn taxed_samples_land_the_draw_within_bounds() {
+        let bounds = AmountBounds::new(U256::from(10 * DATA), U256::from(400 * DATA))
+            .with_reserve(U256::from(100 * DATA));
+        let distribution = AmountDistribution::stake(&BehaviorProfile::degen());
+        let mut rng = StdRng::seed_from_u64(7);
+        for balance in [110, 150, 1_000, 100_000] {
+            let balance = U256::from(balance * DATA);
+            let cap = (balance - bounds.reserve).min(bounds.max);
+            for _ in 0..1_000 {
+                let mut drawn = U256::ZERO;
+                let sent = sample_after_tax(balance, &bounds, 1000, |spendable, landing| {
+                    drawn = distribution.sample(spendable, landing, &mut rng);
+                    drawn
+                });
+                assert_eq!(net_for_gross(sent, 1000), drawn);
+                assert!(sent.is_zero() || (sent >= bounds.min && sent <= cap), "{sent}");
+            }
+        }
+
+        // Untaxed, the balance and bounds are passed through
+        let sent = sample_after_tax(U256::from(5), &bounds, 0, |balance, landing| {
+            assert_eq!(landing, &bounds);
+            balance
+        });
+        assert_eq!(sent, U256::from(5));
+    }
+
+    #[test]
+    fn median_and_spread_follow_the_profile() {
+        let balance = U256::from(1_000_000 * DATA);
+        let bounds = AmountBounds::new(U256::from(1), U256::MAX);
+        let whale = samples(
+            AmountDistribution::stake(&BehaviorProfile::whale()),
+            balance,
+            &bounds,
+        );
+        let degen = samples(
+            AmountDistribution::stake(&BehaviorProfile::degen()),
+            balance,
+            &bounds,
+        );
+
+        let median = |samples: &[U256]| samples[SAMPLES / 2];
+        let spread = |samples: &[U256]| {
+            let (p10, p90) = (samples[SAMPLES / 10], samples[SAMPLES * 9 / 10]);
+            u128::try_from(p90 / p10).unwrap_or(u128::MAX)
+        };
+        assert!(median(&whale) > median(&degen) * U256::from(2));
+        assert!(spread(&degen) > spread(&whale));
+
+        // Rounding down keeps the median within ~15% below the design median
+        let design = percentage_of(
+            balance,
+            pct_to_bps(AmountDistribution::stake(&BehaviorProfile::whale()).median_share),
+        );
+        assert!(median(&whale) <= design, "{} > {design}", median(&whale));
+        assert!(median(&whale) >= design * U256::from(85) / U256::from(100));
+
+        // Bets grow with risk tolerance
+        let bet = |profile: &BehaviorProfile| {
+            median(&samples(
+                AmountDistribution::bet(profile, 0.05),
+                balance,
+                &bounds,
+            ))
+        };
+        assert!(bet(&BehaviorProfile::degen()) > bet(&BehaviorProfile::whale()));
+    }
+
+    #[test]
+    fn amounts_are_rounded() {
+        let balance = U256::from(12_345 * DATA);
+        let bounds = AmountBounds::new(U256::from(1), U256::MAX);
+        let amounts = samples(
+            AmountDistribution::stake(&BehaviorProfile::grinder()),
+            balance,
+            &bounds,
+        );
+
+        for amount in &amounts {
+            assert_eq!(
+                *amount,
+                two_significant_digits(*amount),
+                "{amount} not rounded"
+            );
+        }
+        let round = amounts
+            .iter()
+            .filter(|amount| round_figure(**amount) == **amount)
+            .count();
+        assert!(round > SAMPLES / 10, "only {round} round figures");
+    }
+
+    #[test]
+    fn rounding_never_exceeds_the_cap() {
+        // A cap that is not round itself
+        let bounds = AmountBounds::new(U256::from(DATA), U256::from(123 * DATA + 456));
+        let amounts = samples(
+            AmountDistribution::stake(&BehaviorProfile::whale()).scaled(10.0),
+            U256::from(1_000 * DATA),
+            &bounds,
+        );
+        assert!(amounts.iter().all(|amount| *amount <= bounds.max));
+        assert_eq!(amounts.last(), Some(&U256::from(120 * DATA)));
+    }
+
+    #[test]
+    fn rounding_helpers() {
+        assert_eq!(
+            two_significant_digits(U256::from(123_456)),
+            U256::from(120_000)
+        );
+        assert_eq!(two_significant_digits(U256::from(99)), U256::from(99));
+        assert_eq!(two_significant_digits(U256::from(7)), U256::from(7));
+        assert_eq!(round_figure(U256::from(123_456)), U256::from(100_000));
+        assert_eq!(round_figure(U256::from(480)), U256::from(200));
+        assert_eq!(round_figure(U256::from(9_999)), U256::from(5_000));
+        assert_eq!(round_figure(U256::ZERO), U256::ZERO);
+    }
+}
@@ -0,0 +1,392 @@
+//! What a GhostCore position is worth.
+//!
+//! Deciders keep asking the same things of a position: what would extracting
+//! it bring in now, how likely is it to be traced in its next scan, and is
+//! holding through that scan expected to be worth more than leaving?
+//! [`PositionValuation`] answers them in one place.
+//!
+//! Amounts are DATA in wei, worked out in `U256` and basis points. Only the
+//! risk score is a float, for deciders that weigh it against profile traits.
+//!
+//! # Holding for One More Scan
+//!
+//! Extracting now brings in the stake less the [exit toll](ExitToll) plus
+//! the pending rewards, less the [transfer tax](crate::state::TransferTax)
+//! on what GhostCore pays out. Holding through the next scan brings in the
+//! same plus one more scan's rewards if the position survives, and nothing
+//! if it is traced:
+//!
+//! ```text
+//! exit_value = (stake - toll + pending_rewards) - tax
+//! hold_value = ((stake - toll + pending_rewards + reward_per_scan) - tax) * (1 - death_rate)
+//! ```
+//!
+//! One scan's rewards are estimated as the pending rewards spread over the
+//! scans survived so far.
+//!
+//! # Death Rates
+//!
+//! The death rate comes from a [`RiskModel`]. [`DeathRateTable`] looks it up
+//! per level, defaulting to the protocol's base rates; a model fed with
+//! observed outcomes (e.g. indexer stats) can take its place later.
+
+use alloy::primitives::{I256, U256};
+use serde::{Deserialize, Serialize};
+
+use super::{BPS_100_PERCENT, net_for_gross, percentage_of};
+use crate::state::{ExitToll, Level, Position};
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// RISK MODELS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Source of the chance a position is traced in its next scan.
+pub trait RiskModel: Send + Sync + std::fmt::Debug {
+    /// Death rate of `position` in its next scan (basis points, at most
+    /// 10000).
+    fn death_rate_bps(&self, position: &Position) -> u16;
+}
+
+/// Death rates per level, each defaulting to the level's base rate.
+///
+/// Boosts lower a position's effective death rate below its level's base
+/// rate; a configured rate is lowered by the same proportion.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeathRateTable {
+    /// THE VAULT (basis points).
+    #[serde(default)]
+    pub vault: Option<u16>,
+
+    /// MAINFRAME (basis points).
+    #[serde(default)]
+    pub mainframe: Option<u16>,
+
+    /// SUBNET (basis points).
+    #[serde(default)]
+    pub subnet: Option<u16>,
+
+    /// DARKNET (basis points).
+    #[serde(default)]
+    pub darknet: Option<u16>,
+
+    /// BLACK ICE (basis points).
+    #[serde(default)]
+    pub black_ice: Option<u16>,
+}
+
+impl DeathRateTable {
+    /// A table of the protocol's base rates.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            vault: None,
+            mainframe: None,
+            subnet: None,
+            darknet: None,
+            black_ice: None,
+        }
+    }
+
+    /// Death rate of `level` before boosts (basis points).
+    #[must_use]
+    pub fn level_rate_bps(&self, level: Level) -> u16 {
+        let configured = match level {
+            Level::None => None,
+            Level::Vault => self.vault,
+            Level::Mainframe => self.mainframe,
+            Level::Subnet => self.subnet,
+            Level::Darknet => self.darknet,
+            Level::BlackIce => self.black_ice,
+        };
+        configured
+            .unwrap_or_else(|| level.base_death_rate_bps())
+            .min(BPS)
+    }
+}
+
+impl RiskModel for DeathRateTable {
+    fn death_rate_bps(&self, position: &Position) -> u16 {
+        let rate = self.level_rate_bps(position.level);
+        let base = position.level.base_death_rate_bps();
+        if base == 0 {
+            return rate;
+        }
+        let effective = position.effective_death_rate_bps.min(base);
+        let scaled = u32::from(rate) * u32::from(effective) / u32::from(base);
+        u16::try_from(scaled).unwrap_or(BPS)
+    }
+}
+
+/// Basis points representing 100%, as the width of death rates.
+const BPS: u16 = 10_000;
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// POSITION VALUATION
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// A position's worth now and over its next scan.
+///
+/// See the [module documentation](self) for how it is worked out.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PositionValuation {
+    /// Staked DATA (in wei).
+    pub stake: U256,
+
+    /// Rewards accrued so far (in wei).
+    pub pending_rewards: U256,
+
+    /// DATA withheld on extraction (in wei).
+    pub exit_toll: U256,
+
+    /// DATA taxed off the payout of extracting now (in wei).
+    #[serde(default)]
+    pub exit_tax: U256,
+
+    /// DATA extracting now would bring in (in wei).
+    pub exit_value: U256,
+
+    /// Estimated rewards of one more scan (in wei).
+    pub reward_per_scan: U256,
+
+    /// Chance of being traced in the next scan (basis points).
+    pub death_rate_bps: u16,
+
+    /// [`death_rate_bps`](Self::death_rate_bps) as a 0.0 - 1.0 score.
+    pub cull_risk: f64,
+
+    /// Expected DATA of holding through the next scan, then extracting (in
+    /// wei).
+    pub hold_value: U256,
+}
+
+impl PositionValuation {
+    /// Value `position` under `toll`, with its death rate from `risk`.
+    #[must_use]
+    pub fn of(position: &Position, toll: ExitToll, risk: &(impl RiskModel + ?Sized)) -> Self {
+        Self::of_taxed(position, toll, 0, risk)
+    }
+
+    /// Value `position` under `toll` and a transfer tax of `tax_bps` on its
+    /// payout, with its death rate from `risk`.
+    #[must_use]
+    pub fn of_taxed(
+        position: &Position,
+        toll: ExitToll,
+        tax_bps: u64,
+        risk: &(impl RiskModel + ?Sized),
+    ) -> Self {
+        let stake = position.amount;
+        let pending_rewards = position.pending_rewards;
+        let exit_toll = toll.on(stake);
+        let payout = stake
+            .saturating_sub(exit_toll)
+            .saturating_add(pending_rewards);
+        let exit_value = net_for_gross(payout, tax_bps);
+
+        let scans = U256::from(position.ghost_streak.max(1));
+        let reward_per_scan = pending_rewards / scans;
+
+        let death_rate_bps = risk.death_rate_bps(position).min(BPS);
+        let survival_bps = BPS_100_PERCENT - u64::from(death_rate_bps);
+        let held_payout = net_for_gross(payout.saturating_add(reward_per_scan), tax_bps);
+        let hold_value = percentage_of(held_payout, survival_bps);
+
+        Self {
+            stake,
+            pending_rewards,
+            exit_toll,
+            exit_tax: payout - exit_value,
+            exit_value,
+            reward_per_scan,
+            death_rate_bps,
+            cull_risk: f64::from(death_rate_bps) / f64::from(BPS),
+            hold_value,
+        }
+    }
+
+    /// Expected gain of holding through the next scan over extracting now
+    /// (in wei); negative if extracting is worth more.
+    #[must_use]
+    pub fn hold_advantage(&self) -> I256 {
+        let signed = |amount: U256| I256::try_from(amount).unwrap_or(I256::MAX);
+        signed(self.hold_value).saturating_sub(signed(self.exit_value))
+    }
+
+    /// How much more extracting now is worth than holding, in basis points
+    /// of the exit value; zero if holding is worth as much or more.
+    #[must_use]
+    pub fn extract_edge_bps(&self) -> u64 {
+        if self.exit_value.is_zero() || self.hold_value >= self.exit_value {
+            return 0;
+        }
+        let edge =
+            (self.exit_value - self.hold_value) * U256::from(BPS_100_PERCENT) / self.exit_value;
+        u64::try_from(edge).unwrap_or(BPS_100_PERCENT)
+    }
+
+    /// Check if extracting now is worth more than holding.
+    #[must_use]
+    pub fn favors_extract(&self) -> bool {
+        self.extract_edge_bps() > 0
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::state::{ActiveBoost, BoostType};
+
+    const DATA: u128 = 1_000_000_000_000_000_000;
+
+    fn data(amount: u128) -> U256 {
+        U256::from(amount * DATA)
+    }
+
+    fn position(level: Level, stake: u128, rewards: u128, streak: u16) -> Position {
+        Position {
+            amount: data(stake),
+            level,
+            entry_timestamp: 0,
+            last_add_timestamp: 0,
+            alive: true,
+            ghost_streak: streak,
+            pending_rewards: data(rewards),
+            effective_death_rate_bps: level.base_death_rate_bps(),
+            in_lock_period: false,
+            active_boosts: Vec::new(),
+        }
+    }
+
+    const fn toll(penalty_bps: u16) -> ExitToll {
+        ExitToll { penalty_bps }
+    }
+
+    #[test]
+    fn values_position_without_toll() {
+        // SUBNET, 1000 staked, 40 pending over 4 scans
+        let valuation = PositionValuation::of(
+            &position(Level::Subnet, 1000, 40, 4),
+            toll(0),
+            &DeathRateTable::new(),
+        );
+
+        assert_eq!(valuation.exit_toll, U256::ZERO);
+        assert_eq!(valuation.exit_value, data(1040));
+        assert_eq!(valuation.reward_per_scan, data(10));
+        assert_eq!(valuation.death_rate_bps, 2500);
+        assert!((valuation.cull_risk - 0.25).abs() < f64::EPSILON);
+
+        // 1050 * 75% = 787.5, so extracting is worth 252.5 / 1040 more
+        assert_eq!(valuation.hold_value, U256::from(7875 * DATA / 10));
+        assert_eq!(valuation.extract_edge_bps(), 2427);
+        assert_eq!(
+            valuation.hold_advantage(),
+            -I256::try_from(2525 * DATA / 10).unwrap()
+        );
+        assert!(valuation.favors_extract());
+    }
+
+    #[test]
+    fn boosted_low_risk_position_is_worth_holding() {
+        // VAULT with its death rate halved, 100 staked, 30 pending over 3
+        // scans, 10% toll
+        let mut boosted = position(Level::Vault, 100, 30, 3);
+        boosted.effective_death_rate_bps = 250;
+        boosted.active_boosts.push(ActiveBoost {
+            boost_type: BoostType::DeathReduction,
+            value_bps: 5000,
+            expiry: u64::MAX,
+        });
+        let valuation = PositionValuation::of(&boosted, toll(1000), &DeathRateTable::new());
+
+        assert_eq!(valuation.exit_toll, data(10));
+        assert_eq!(valuation.exit_value, data(120));
+        assert_eq!(valuation.death_rate_bps, 250);
+
+        // 130 * 97.5% = 126.75
+        assert_eq!(valuation.hold_value, U256::from(12675 * DATA / 100));
+        assert_eq!(
+            valuation.hold_advantage(),
+            I256::try_from(675 * DATA / 100).unwrap()
+        );
+        assert_eq!(valuation.extract_edge_bps(), 0);
+        assert!(!valuation.favors_extract());
+    }
+
+    #[test]
+    fn tolls_lower_the_exit_value() {
+        let position = position(Level::Mainframe, 200, 0, 0);
+        let table = DeathRateTable::new();
+
+        // Without rewards, holding is worth 85% of leaving whatever the toll
+        for (penalty_bps, exit) in [(0, 200), (2500, 150), (10_000, 0)] {
+            let valuation = PositionValuation::of(&position, toll(penalty_bps), &table);
+            assert_eq!(valuation.exit_value, data(exit), "{penalty_bps} bps");
+            assert_eq!(valuation.hold_value, percentage_of(data(exit), 8500));
+        }
+
+        // Nothing left to extract is not worth extracting
+        let wiped = PositionValuation::of(&position, toll(10_000), &table);
+        assert_eq!(wiped.extract_edge_bps(), 0);
+
+        // Penalties above 100% take the stake, not more
+        let over = PositionValuation::of(&position, toll(u16::MAX), &table);
+        assert_eq!(over.exit_toll, data(200));
+    }
+
+    #[test]
+    fn taxes_the_payout() {
+        // 10% tax on the 1040 paid out, and on the 1050 after one more scan
+        let position = position(Level::Subnet, 1000, 40, 4);
+        let table = DeathRateTable::new();
+        let valuation = PositionValuation::of_taxed(&position, toll(0), 1000, &table);
+
+        assert_eq!(valuation.exit_tax, data(104));
+        assert_eq!(valuation.exit_value, data(936));
+        assert_eq!(valuation.hold_value, percentage_of(data(945), 7500));
+
+        let untaxed = PositionValuation::of(&position, toll(0), &table);
+        assert_eq!(untaxed.exit_tax, U256::ZERO);
+        assert_eq!(untaxed.exit_value, data(1040));
+    }
+
+    #[test]
+    fn configured_death_rates_scale_with_boosts() {
+        let table = DeathRateTable {
+            darknet: Some(4000),
+            black_ice: Some(12_000),
+            ..DeathRateTable::new()
+        };
+
+        // DARKNET's base rate is 3500; a boost down to 1750 halves 4000 too
+        let mut darknet = position(Level::Darknet, 500, 50, 5);
+        assert_eq!(table.death_rate_bps(&darknet), 4000);
+        darknet.effective_death_rate_bps = 1750;
+        assert_eq!(table.death_rate_bps(&darknet), 2000);
+
+        // 550 + 10 at 80% = 448, an edge of 102 / 550
+        let valuation = PositionValuation::of(&darknet, toll(0), &table);
+        assert_eq!(valuation.hold_value, data(448));
+        assert_eq!(valuation.extract_edge_bps(), 1854);
+
+        // Rates are capped at 100%, and unconfigured levels use base rates
+        let black_ice = position(Level::BlackIce, 100, 0, 1);
+        assert_eq!(table.death_rate_bps(&black_ice), 10_000);
+        let valuation = PositionValuation::of(&black_ice, toll(0), &table);
+        assert_eq!(valuation.hold_value, U256::ZERO);
+        assert_eq!(valuation.extract_edge_bps(), 10_000);
+        assert_eq!(table.level_rate_bps(Level::Subnet), 2500);
+    }
+
+    #[test]
+    fn parses_table_from_config() {
+        let table: DeathRateTable = serde_json::from_str(r#"{"subnet": 3000}"#).unwrap();
+        assert_eq!(table.level_rate_bps(Level::Subnet), 3000);
+        assert_eq!(table.level_rate_bps(Level::Vault), 500);
+    }
+}
@@ -3,25 +3,51 @@
 //! This module provides the [`GhostnetPlugin`] which implements the
 //! [`ActionPlugin`](fleet_core::ActionPlugin) trait.
 
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
 
 use alloy::primitives::{Address, Bytes, U256};
 use async_trait::async_trait;
-use evm_provider::ChainProvider;
-use fleet_core::plugins::{Action, ActionId, ActionPlugin, ActionResult, PluginContext};
+use evm_provider::error::revert_reason;
+use evm_provider::{ChainProvider, TransactionReceipt, TransactionRequest};
+use fleet_core::plugins::{
+    Action, ActionId, ActionPlugin, ActionRequirements, ActionResult, DataFlow, PluginActivity,
+    PluginContext,
+};
 use fleet_core::profiles::BehaviorProfile;
-use fleet_core::wallet::WalletState;
+use fleet_core::safety::Spend;
+use fleet_core::wallet::{PluginState, WalletState};
 use tracing::{debug, info, instrument, warn};
 
 use crate::actions::ghost_core::{
-    ACTION_ADD_STAKE, ACTION_CLAIM_REWARDS, ACTION_EXTRACT, ACTION_JACK_IN,
+    ACTION_ADD_STAKE, ACTION_APPROVE, ACTION_CLAIM_REWARDS, ACTION_EXTRACT, ACTION_JACK_IN,
 };
+use crate::actions::arcade::ACTION_ARCADE_PLAY;
+use crate::actions::boost::ACTION_APPLY_BOOST;
+use crate::actions::dead_pool::ACTION_DEAD_POOL_BET;
 use crate::actions::hashcrash::ACTION_HASHCRASH_BET;
-use crate::actions::{GhostCoreDecider, HashCrashDecider};
+use crate::actions::{
+    ACTIONS, ArcadeDecider, BoostDecider, DeadPoolDecider, GhostCoreDecider, HashCrashDecider,
+    RetirementDecider, WarmupDecider,
+};
 use crate::config::GhostnetConfig;
-use crate::contracts::GhostnetContracts;
+use crate::contracts::receipt::{
+    GhostnetEvent, apply_events, data_received, parse_ghostnet_events,
+};
+use crate::contracts::{
+    decode_active_boosts, decode_arcade_games, decode_dead_pool_round, decode_player_bet_amount,
+    decode_reset_timer, decode_round, decode_round_count, decode_tax_exclusion, decode_tax_rate,
+    GhostnetContracts, MULTIPLIER_PRECISION,
+};
 use crate::error::{GhostnetError, Result};
-use crate::state::{GhostnetState, Level};
+use crate::math::net_for_gross;
+use crate::state::{
+    ActiveBoost, ArcadeGame, BetOutcome, BetRecord, BoostOffer, DeadPoolRound, ExitToll,
+    GhostnetState, Level, PnlLedger, ResetTimer, TransferTax,
+};
+
+/// ID of the plugin, under which its [`GhostnetState`] is stored on wallets.
+pub const PLUGIN_ID: &str = "ghostnet";
 
 // ═══════════════════════════════════════════════════════════════════════════════
 // GHOSTNET PLUGIN
@@ -30,7 +56,8 @@ use crate::state::{GhostnetState, Level};
 /// GHOSTNET protocol action plugin.
 ///
 /// This plugin implements the `ActionPlugin` trait for GHOSTNET protocol
-/// interactions, including GhostCore staking and HashCrash arcade games.
+/// interactions, including GhostCore staking, HashCrash and the other
+/// ArcadeCore games, and DeadPool prediction rounds.
 ///
 /// # Actions
 ///
@@ -38,7 +65,100 @@ use crate::state::{GhostnetState, Level};
 /// - `ghostnet.add_stake`: Add to existing position
 /// - `ghostnet.extract`: Exit position and claim rewards
 /// - `ghostnet.claim_rewards`: Claim pending rewards
+/// - `ghostnet.apply_boost`: Apply a signed boost grant to the position
 /// - `ghostnet.hashcrash_bet`: Place a bet in HashCrash
+/// - `ghostnet.arcade_play`: Enter a game registered with ArcadeCore
+/// - `ghostnet.dead_pool_bet`: Bet OVER or UNDER in a DeadPool round
+///
+/// Amounts are sized from the wallet's tracked DATA balance. If that balance
+/// is older than `behavior.max_balance_age_secs`, the plugin returns a
+/// [balance refresh request](Action::refresh_balances) instead of acting.
+///
+/// While the wallet has a [warm-up plan](fleet_core::wallet::WarmupPlan), it
+/// only takes the plan's due step, see [`WarmupDecider`]. A retiring wallet
+/// only extracts and claims, see [`RetirementDecider`], and counts as having a
+/// position open as long as it has one or its HashCrash bets are unsettled.
+///
+/// Once an action is mined, the position's level, stake and streak are taken
+/// from the GhostCore events in its receipt (see
+/// [`receipt`](crate::contracts::receipt)). If the receipt carries none, the
+/// state is read from chain as usual.
+///
+/// # Cooldowns
+///
+/// | Action | Default cooldown |
+/// |--------|------------------|
+/// | `jack_in`, `add_stake`, `apply_boost` | 1 hour |
+/// | `claim_rewards` | 4 hours |
+/// | `hashcrash_bet`, `arcade_play`, `dead_pool_bet` | 15 minutes |
+/// | `extract` | none |
+///
+/// Candidates on cooldown are skipped in favour of the next decider.
+///
+/// # HashCrash PnL
+///
+/// Bets that make it on chain are kept in a per-wallet [`PnlLedger`],
+/// reported in the wallet's plugin state by `read_state` and in the `pnl`
+/// detail of bet results. Pending bets are resolved against the contract by
+/// [`reconcile_bets`](Self::reconcile_bets), and before each decision for the
+/// wallet; the ledger's losing streak scales down later bets.
+///
+/// # Boosts
+///
+/// Boost grants handed to [`offer_boost`](Self::offer_boost) are applied by
+/// the `apply_boost` action when worth it (see `BoostDecider`). Boosts the
+/// plugin applied count as active on the position until they expire, so a
+/// boost type is never bought twice.
+///
+/// # Exit Toll
+///
+/// Extractions are weighed net of GhostCore's exit toll, read before each
+/// decision for a wallet with a position and reused for
+/// `behavior.exit_toll_ttl_secs`. If it cannot be read, the decision goes on
+/// with `behavior.default_exit_toll_bps`.
+///
+/// # System Reset
+///
+/// GhostCore's reset timer is read before each decision as well, reused for
+/// the shorter `behavior.reset_timer_ttl_secs`. Within
+/// `behavior.reset_danger_secs` of the reset, wallets stake nothing and get
+/// their positions out. A position read in an earlier reset epoch than the
+/// timer's is not acted on until the wallet's state is read again, and the
+/// boosts the plugin applied to it are forgotten.
+///
+/// # Transfer Tax
+///
+/// DataToken's tax rate and whether the wallet and the game contracts are
+/// excluded from it are read before each decision, reused for
+/// `behavior.transfer_tax_ttl_secs`. Stakes and bets are sized so that the
+/// intended amount lands after the tax, and bet payouts and extractions are
+/// valued net of it. Excluded wallets, and games excluded as deployed,
+/// are untaxed. While the rate cannot be read, nothing is taxed.
+///
+/// # Partial Extraction
+///
+/// With an `extract_strategy` other than `full`, positions are extracted in
+/// parts, tracked per position. Before the first partial extraction, a call
+/// of `extract(amount)` checks that GhostCore takes an amount to extract.
+/// If it reverts without a reason, as calls of functions a contract does not
+/// have do, every extraction is in full for the rest of the session.
+///
+/// # Arcade Games
+///
+/// The games registered with ArcadeCore are listed before each decision and
+/// narrowed down to those allowed by `arcade_games` in the config. If the
+/// listing fails, e.g. because ArcadeCore is not deployed on the chain, the
+/// wallet simply has no `arcade_play` action.
+///
+/// # DeadPool
+///
+/// With a `dead_pool` address in the config, the latest
+/// `behavior.dead_pool.lookback_rounds` rounds are read before each decision
+/// and those still open for bets handed to [`DeadPoolDecider`], each with
+/// the time the plugin first saw it open as the start of its betting
+/// window. A wallet bets on a round once. Just before a bet is sent its
+/// round is read again, and the bet is skipped if the round closed or the
+/// bet's payout fell by more than `behavior.dead_pool.max_odds_drift_bps`.
 ///
 /// # Example
 ///
@@ -59,6 +179,94 @@ pub struct GhostnetPlugin<P: ChainProvider> {
 
     /// Chain provider.
     provider: Arc<P>,
+
+    /// What the plugin tracks per wallet address.
+    wallets: Mutex<HashMap<Address, TrackedWallet>>,
+
+    /// The last reset timer read from GhostCore, and when.
+    reset_timer: Mutex<Option<(ResetTimer, chrono::DateTime<chrono::Utc>)>>,
+
+    /// Whether GhostCore takes an amount to extract, once known.
+    partial_extract: Mutex<Option<bool>>,
+
+    /// DataToken's tax rate and exclusions, and when they were read.
+    transfer_tax: Mutex<TaxCache>,
+
+    /// When each DeadPool round was first seen open (Unix timestamp).
+    dead_pool_seen: Mutex<HashMap<u64, u64>>,
+}
+
+/// What the plugin last read of DataToken's transfer tax.
+#[derive(Debug, Default)]
+struct TaxCache {
+    /// Tax rate (basis points), and when it was read.
+    rate: Option<(u16, chrono::DateTime<chrono::Utc>)>,
+
+    /// Whether an address is excluded from the tax, and when it was read.
+    excluded: HashMap<Address, (bool, chrono::DateTime<chrono::Utc>)>,
+}
+
+/// What the plugin tracks for a wallet on top of the state it reads.
+#[derive(Debug, Clone, Default)]
+struct TrackedWallet {
+    /// HashCrash bets.
+    pnl: PnlLedger,
+
+    /// Boost grants not applied yet.
+    boost_offers: Vec<BoostOffer>,
+
+    /// Boosts the plugin applied.
+    boosts: Vec<ActiveBoost>,
+
+    /// DATA spent on boost grants (in wei).
+    boost_spent: U256,
+
+    /// DATA extracted in part from the position entered at a timestamp.
+    extracted: Option<(u64, U256)>,
+
+    /// DATA flows seen in receipts, for activity reports.
+    flows: DataFlows,
+
+    /// DeadPool rounds bet on.
+    dead_pool_bets: Vec<u64>,
+}
+
+/// DATA a wallet moved in and out of GhostCore, as its receipts show (in
+/// wei).
+///
+/// Positions lost to a scan emit no event for the wallet, so stakes that
+/// died are not counted as lost.
+#[derive(Debug, Clone, Copy, Default)]
+struct DataFlows {
+    /// Entered or added to positions.
+    staked: U256,
+
+    /// Extracted, rewards included, or returned from a culled position.
+    extracted: U256,
+
+    /// Penalties taken from culled positions.
+    culled: U256,
+}
+
+impl DataFlows {
+    /// Add the flows of `events` concerning `player`.
+    fn record(&mut self, player: Address, events: &[GhostnetEvent]) {
+        for event in events.iter().filter(|event| event.user() == player) {
+            match event {
+                GhostnetEvent::JackedIn(e) => self.staked = self.staked.saturating_add(e.amount),
+                GhostnetEvent::StakeAdded(e) => self.staked = self.staked.saturating_add(e.amount),
+                GhostnetEvent::Extracted(e) => {
+                    let total = e.amount.saturating_add(e.rewards);
+                    self.extracted = self.extracted.saturating_add(total);
+                }
+                GhostnetEvent::PositionCulled(e) => {
+                    self.extracted = self.extracted.saturating_add(e.returnedAmount);
+                    self.culled = self.culled.saturating_add(e.penaltyAmount);
+                }
+                GhostnetEvent::BoostApplied(_) => {}
+            }
+        }
+    }
 }
 
 impl<P: ChainProvider> std::fmt::Debug for GhostnetPlugin<P> {
@@ -80,6 +288,11 @@ impl<P: ChainProvider> GhostnetPlugin<P> {
             config,
             contracts,
             provider,
+            wallets: Mutex::new(HashMap::new()),
+            reset_timer: Mutex::new(None),
+            partial_extract: Mutex::new(None),
+            transfer_tax: Mutex::new(TaxCache::default()),
+            dead_pool_seen: Mutex::new(HashMap::new()),
         }
     }
 
@@ -101,13 +314,704 @@ impl<P: ChainProvider> GhostnetPlugin<P> {
         &self.provider
     }
 
-    /// Parse GHOSTNET state from wallet plugin state.
-    fn parse_state(wallet: &WalletState) -> GhostnetState {
-        wallet
-            .plugin_state_as::<GhostnetState>("ghostnet")
+    /// Get a wallet's HashCrash ledger.
+    #[must_use]
+    pub fn pnl(&self, player: Address) -> PnlLedger {
+        self.tracked(player).pnl
+    }
+
+    /// Record a HashCrash bet that made it on chain.
+    pub fn record_bet(
+        &self,
+        player: Address,
+        round_id: u64,
+        amount: U256,
+        target_multiplier: u16,
+        placed_at: u64,
+    ) {
+        self.update(player, |wallet| {
+            wallet
+                .pnl
+                .record_bet(round_id, amount, target_multiplier, placed_at);
+        });
+    }
+
+    /// Make a signed boost grant available to a wallet.
+    ///
+    /// Grants come from the boost signer (e.g. the mini-game backend); a grant
+    /// whose nonce the wallet already holds is ignored.
+    pub fn offer_boost(&self, player: Address, offer: BoostOffer) {
+        self.update(player, |wallet| {
+            if wallet.boost_offers.iter().all(|held| held.nonce != offer.nonce) {
+                wallet.boost_offers.push(offer);
+            }
+        });
+    }
+
+    /// Read the boosts active on a wallet's position from GhostCore.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the call fails or returns malformed data.
+    pub async fn read_active_boosts(&self, player: Address) -> Result<Vec<ActiveBoost>> {
+        let call = TransactionRequest::new()
+            .to(self.contracts.ghost_core)
+            .data(self.contracts.encode_get_active_boosts(player));
+        decode_active_boosts(&self.provider.call(&call).await?)
+    }
+
+    /// Read the system reset timer from GhostCore.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the call fails or returns malformed data.
+    pub async fn read_reset_timer(&self) -> Result<ResetTimer> {
+        let call = TransactionRequest::new()
+            .to(self.contracts.ghost_core)
+            .data(self.contracts.encode_get_system_reset());
+        decode_reset_timer(&self.provider.call(&call).await?)
+    }
+
+    /// Read the exit toll from GhostCore.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the call fails or returns malformed data.
+    pub async fn read_exit_toll(&self) -> Result<ExitToll> {
+        self.read_reset_timer().await.map(|timer| timer.exit_toll())
+    }
+
+    /// The reset timer at `now`: the last one read while younger than `ttl`,
+    /// else a fresh read, or `None` if that fails.
+    async fn system_reset(
+        &self,
+        now: chrono::DateTime<chrono::Utc>,
+        ttl: chrono::Duration,
+    ) -> Option<ResetTimer> {
+        let cached = *self.reset_timer.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some((timer, read_at)) = cached
+            && now - read_at < ttl
+        {
+            return Some(timer);
+        }
+        match self.read_reset_timer().await {
+            Ok(timer) => {
+                if let Some((previous, _)) = cached
+                    && previous.epoch < timer.epoch
+                {
+                    info!(epoch = timer.epoch, "GhostCore system reset observed");
+                }
+                *self.reset_timer.lock().unwrap_or_else(PoisonError::into_inner) =
+                    Some((timer, now));
+                Some(timer)
+            }
+            Err(e) => {
+                warn!(error = %e, "Failed to read GhostCore's reset timer");
+                None
+            }
+        }
+    }
+
+    /// The exit toll at `now`, reused for `behavior.exit_toll_ttl_secs`, or
+    /// the configured default if it cannot be read.
+    async fn exit_toll(&self, now: chrono::DateTime<chrono::Utc>) -> ExitToll {
+        let behavior = &self.config.behavior;
+        self.system_reset(now, behavior.exit_toll_ttl())
+            .await
+            .map_or(
+                ExitToll {
+                    penalty_bps: behavior.default_exit_toll_bps,
+                },
+                |timer| timer.exit_toll(),
+            )
+    }
+
+    /// The reset timer at `now`, reused for `behavior.reset_timer_ttl_secs`.
+    async fn reset_timer(&self, now: chrono::DateTime<chrono::Utc>) -> Option<ResetTimer> {
+        self.system_reset(now, self.config.behavior.reset_timer_ttl())
+            .await
+    }
+
+    /// Read DataToken's transfer tax rate (basis points).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the call fails or returns malformed data.
+    pub async fn read_tax_rate(&self) -> Result<u16> {
+        let call = TransactionRequest::new()
+            .to(self.contracts.data_token)
+            .data(self.contracts.encode_tax_rate());
+        decode_tax_rate(&self.provider.call(&call).await?)
+    }
+
+    /// Read whether `account` is excluded from DataToken's transfer tax.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the call fails or returns malformed data.
+    pub async fn read_tax_exclusion(&self, account: Address) -> Result<bool> {
+        let call = TransactionRequest::new()
+            .to(self.contracts.data_token)
+            .data(self.contracts.encode_is_excluded_from_tax(account));
+        decode_tax_exclusion(&self.provider.call(&call).await?)
+    }
+
+    /// The tax rate at `now`: the last one read while younger than
+    /// `behavior.transfer_tax_ttl_secs`, else a fresh read, or the last one
+    /// read, if any, when that fails.
+    async fn tax_rate(&self, now: chrono::DateTime<chrono::Utc>) -> Option<u16> {
+        let cached = self.lock_tax().rate;
+        if let Some((rate, read_at)) = cached
+            && now - read_at < self.config.behavior.transfer_tax_ttl()
+        {
+            return Some(rate);
+        }
+        match self.read_tax_rate().await {
+            Ok(rate) => {
+                self.lock_tax().rate = Some((rate, now));
+                Some(rate)
+            }
+            Err(e) => {
+                warn!(error = %e, "Failed to read DataToken's tax rate");
+                cached.map(|(rate, _)| rate)
+            }
+        }
+    }
+
+    /// Whether `account` is excluded from the tax at `now`, cached like
+    /// [`tax_rate`](Self::tax_rate).
+    async fn tax_excluded(
+        &self,
+        account: Address,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Option<bool> {
+        let cached = self.lock_tax().excluded.get(&account).copied();
+        if let Some((excluded, read_at)) = cached
+            && now - read_at < self.config.behavior.transfer_tax_ttl()
+        {
+            return Some(excluded);
+        }
+        match self.read_tax_exclusion(account).await {
+            Ok(excluded) => {
+                self.lock_tax().excluded.insert(account, (excluded, now));
+                Some(excluded)
+            }
+            Err(e) => {
+                warn!(account = %account, error = %e, "Failed to read DataToken's tax exclusion");
+                cached.map(|(excluded, _)| excluded)
+            }
+        }
+    }
+
+    /// The tax on DATA `wallet` moves to and from the games at `now`.
+    ///
+    /// Untaxed if the rate is unknown or the wallet is excluded; a contract
+    /// whose exclusion is unknown is taken to be taxed.
+    async fn transfer_tax(
+        &self,
+        wallet: Address,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> TransferTax {
+        let rate = match self.tax_rate(now).await {
+            Some(rate) if rate > 0 => rate,
+            _ => return TransferTax::default(),
+        };
+        if self.tax_excluded(wallet, now).await == Some(true) {
+            return TransferTax::default();
+        }
+        let taxed = |excluded: Option<bool>| if excluded == Some(true) { 0 } else { rate };
+        TransferTax {
+            ghost_core_bps: taxed(self.tax_excluded(self.contracts.ghost_core, now).await),
+            arcade_core_bps: taxed(self.tax_excluded(self.contracts.arcade_core, now).await),
+        }
+    }
+
+    /// Lock the transfer tax cache.
+    fn lock_tax(&self) -> MutexGuard<'_, TaxCache> {
+        self.transfer_tax.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// What lands of `amount` ArcadeCore pays out to `player`.
+    async fn arcade_payout(&self, player: Address, amount: U256) -> U256 {
+        let tax = self.transfer_tax(player, chrono::Utc::now()).await;
+        net_for_gross(amount, u64::from(tax.arcade_core_bps))
+    }
+
+    /// Read the games registered with ArcadeCore and open for play.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the call fails or returns malformed data, as it does
+    /// when ArcadeCore is not deployed.
+    pub async fn read_arcade_games(&self) -> Result<Vec<ArcadeGame>> {
+        let call = TransactionRequest::new()
+            .to(self.contracts.arcade_core)
+            .data(self.contracts.encode_get_games());
+        decode_arcade_games(&self.provider.call(&call).await?)
+    }
+
+    /// The games a wallet may play: those allowed by the config, or none if
+    /// ArcadeCore cannot be read.
+    async fn playable_games(&self) -> Vec<ArcadeGame> {
+        match self.read_arcade_games().await {
+            Ok(games) => games
+                .into_iter()
+                .filter(|game| self.config.arcade_games.allows(game.game_id))
+                .collect(),
+            Err(e) => {
+                debug!(error = %e, "ArcadeCore games unavailable");
+                Vec::new()
+            }
+        }
+    }
+
+    /// Read the ID of DeadPool's latest round.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no DeadPool is configured, or the call fails or
+    /// returns malformed data.
+    pub async fn read_round_count(&self) -> Result<u64> {
+        decode_round_count(
+            &self
+                .dead_pool_view(self.contracts.encode_round_count())
+                .await?,
+        )
+    }
+
+    /// Read a DeadPool round, or `None` if it does not exist or was resolved.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no DeadPool is configured, or the call fails or
+    /// returns malformed data.
+    pub async fn read_dead_pool_round(&self, round_id: u64) -> Result<Option<DeadPoolRound>> {
+        let calldata = self.contracts.encode_get_dead_pool_round(round_id);
+        let round = decode_dead_pool_round(round_id, &self.dead_pool_view(calldata).await?)?;
+        Ok(round.map(|round| DeadPoolRound {
+            opened_at: self.first_seen(round_id, None),
+            ..round
+        }))
+    }
+
+    /// The DeadPool rounds `player` may bet on at `now`: the latest
+    /// `lookback_rounds` ones that are open and not bet on by the wallet yet.
+    /// Rounds that cannot be read are left out.
+    async fn open_rounds(
+        &self,
+        player: Address,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Vec<DeadPoolRound> {
+        let count = match self.read_round_count().await {
+            Ok(count) => count,
+            Err(e) => {
+                debug!(error = %e, "DeadPool rounds unavailable");
+                return Vec::new();
+            }
+        };
+        let oldest = count
+            .saturating_sub(self.config.behavior.dead_pool.lookback_rounds)
+            .saturating_add(1);
+        let now = u64::try_from(now.timestamp()).unwrap_or_default();
+        self.dead_pool_seen
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .retain(|round_id, _| *round_id >= oldest);
+        let bets = self.update(player, |tracked| {
+            tracked
+                .dead_pool_bets
+                .retain(|round_id| *round_id >= oldest);
+            tracked.dead_pool_bets.clone()
+        });
+
+        let mut rounds = Vec::new();
+        for round_id in (oldest..=count).filter(|round_id| !bets.contains(round_id)) {
+            match self.read_dead_pool_round(round_id).await {
+                Ok(Some(round)) if round.can_bet(now) => rounds.push(DeadPoolRound {
+                    opened_at: self.first_seen(round_id, Some(now)),
+                    ..round
+                }),
+                Ok(_) => {}
+                Err(e) => debug!(round_id, error = %e, "Failed to read DeadPool round"),
+            }
+        }
+        rounds
+    }
+
+    /// When a DeadPool round was first seen open, recording `now` if it is
+    /// seen for the first time; 0 if it was never seen.
+    fn first_seen(&self, round_id: u64, now: Option<u64>) -> u64 {
+        let mut seen = self
+            .dead_pool_seen
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        match now {
+            Some(now) => *seen.entry(round_id).or_insert(now),
+            None => seen.get(&round_id).copied().unwrap_or_default(),
+        }
+    }
+
+    /// Call a DeadPool view function.
+    async fn dead_pool_view(&self, calldata: Bytes) -> Result<Bytes> {
+        let dead_pool = self
+            .contracts
+            .dead_pool
+            .ok_or_else(|| GhostnetError::InvalidConfig("no DeadPool address".into()))?;
+        let call = TransactionRequest::new().to(dead_pool).data(calldata);
+        Ok(self.provider.call(&call).await?)
+    }
+
+    /// Why a DeadPool bet is no longer worth sending, read from its round as
+    /// it is now, if it is not.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the action's data is malformed or the round
+    /// cannot be read.
+    async fn dead_pool_odds_moved(&self, action: &Action) -> Result<Option<String>> {
+        let (round_id, over, amount) = Self::parse_dead_pool_bet(&action.data)?;
+        let decided_bps = action.data["payout_bps"]
+            .as_u64()
+            .ok_or_else(|| GhostnetError::InvalidActionData("missing payout_bps".into()))?;
+        let round = self.read_dead_pool_round(round_id).await?;
+        Ok(DeadPoolDecider::odds_moved(
+            round.as_ref(),
+            over,
+            amount,
+            decided_bps,
+            &self.config.behavior.dead_pool,
+        ))
+    }
+
+    /// Resolve the pending HashCrash bets of every wallet whose rounds ended.
+    ///
+    /// Wallets whose rounds cannot be read are logged and retried next time.
+    ///
+    /// # Returns
+    ///
+    /// The number of bets resolved.
+    pub async fn reconcile_bets(&self) -> usize {
+        let players: Vec<Address> = self
+            .wallets
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .keys()
+            .copied()
+            .collect();
+
+        let mut resolved = 0;
+        for player in players {
+            match self.reconcile_wallet(player).await {
+                Ok(count) => resolved += count,
+                Err(e) => warn!(player = %player, error = %e, "Failed to reconcile HashCrash bets"),
+            }
+        }
+        resolved
+    }
+
+    /// Resolve a wallet's pending HashCrash bets whose rounds ended.
+    ///
+    /// # Returns
+    ///
+    /// The number of bets resolved.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a round or bet cannot be read from the contract.
+    pub async fn reconcile_wallet(&self, player: Address) -> Result<usize> {
+        let pending: Vec<BetRecord> = self
+            .pnl(player)
+            .bets
+            .into_iter()
+            .filter(|bet| bet.outcome == BetOutcome::Pending)
+            .collect();
+
+        let mut resolved = 0;
+        for bet in pending {
+            let Some((outcome, payout)) = self.bet_outcome(player, &bet).await? else {
+                continue;
+            };
+            if self.update(player, |wallet| wallet.pnl.resolve(bet.round_id, outcome, payout)) {
+                debug!(
+                    round_id = bet.round_id,
+                    ?outcome,
+                    payout = %payout,
+                    "HashCrash bet resolved"
+                );
+                resolved += 1;
+            }
+        }
+        Ok(resolved)
+    }
+
+    /// Read how a pending bet ended, or `None` if its round is still open.
+    async fn bet_outcome(
+        &self,
+        player: Address,
+        bet: &BetRecord,
+    ) -> Result<Option<(BetOutcome, U256)>> {
+        let round = decode_round(&self.view(self.contracts.encode_get_round(bet.round_id)).await?)?;
+        if round.is_refunded() {
+            let refund = self.arcade_payout(player, bet.amount).await;
+            return Ok(Some((BetOutcome::Refunded, refund)));
+        }
+        if !round.is_revealed() {
+            return Ok(None);
+        }
+
+        // Winners get their target multiple of the bet net of rake, and of
+        // the transfer tax once paid out
+        let target = U256::from(bet.target_multiplier);
+        if target >= round.crash_multiplier {
+            return Ok(Some((BetOutcome::Lost, U256::ZERO)));
+        }
+        let calldata = self.contracts.encode_get_player_bet(bet.round_id, player);
+        let net_amount = decode_player_bet_amount(&self.view(calldata).await?)?;
+        let payout = net_amount * target / U256::from(MULTIPLIER_PRECISION);
+        let payout = self.arcade_payout(player, payout).await;
+        Ok(Some((BetOutcome::Won, payout)))
+    }
+
+    /// Call a HashCrash view function.
+    async fn view(&self, calldata: Bytes) -> Result<Bytes> {
+        let call = TransactionRequest::new()
+            .to(self.contracts.hash_crash)
+            .data(calldata);
+        Ok(self.provider.call(&call).await?)
+    }
+
+    /// Record a bet that made it on chain and attach the wallet's ledger to
+    /// its result.
+    fn track_bet(
+        &self,
+        action: &Action,
+        wallet: &WalletState,
+        result: ActionResult,
+    ) -> ActionResult {
+        if result.is_success()
+            && let (Some(round_id), Ok(amount), Some(target)) = (
+                action.data["round_id"].as_u64(),
+                Self::parse_amount(&action.data, "amount"),
+                action.data["target_multiplier"]
+                    .as_u64()
+                    .and_then(|t| u16::try_from(t).ok()),
+            )
+        {
+            #[allow(clippy::cast_sign_loss)]
+            let now = chrono::Utc::now().timestamp() as u64;
+            self.record_bet(wallet.address, round_id, amount, target, now);
+        }
+
+        let ledger = self.pnl(wallet.address);
+        let mut detail = result.detail.clone();
+        if let serde_json::Value::Object(fields) = &mut detail {
+            fields.insert(
+                "pnl".into(),
+                serde_json::json!({
+                    "total_wagered": ledger.total_wagered.to_string(),
+                    "total_won": ledger.total_won.to_string(),
+                    "net": ledger.net.to_string(),
+                    "pending_bets": ledger.pending_rounds().len(),
+                    "loss_streak": ledger.consecutive_losses(),
+                }),
+            );
+        }
+        result.with_detail(detail)
+    }
+
+    /// Record a DeadPool bet that made it on chain, so its round is not bet
+    /// on again.
+    fn track_dead_pool_bet(&self, action: &Action, wallet: &WalletState, result: &ActionResult) {
+        if !result.is_success() {
+            return;
+        }
+        if let Ok((round_id, _, _)) = Self::parse_dead_pool_bet(&action.data) {
+            self.update(wallet.address, |tracked| {
+                tracked.dead_pool_bets.push(round_id);
+            });
+        }
+    }
+
+    /// Record a boost that made it on chain: its grant is used up.
+    fn track_boost(&self, action: &Action, wallet: &WalletState, result: &ActionResult) {
+        if !result.is_success() {
+            return;
+        }
+        let Ok(offer) = Self::parse_offer(&action.data) else {
+            return;
+        };
+        self.update(wallet.address, |tracked| {
+            tracked.boost_offers.retain(|held| held.nonce != offer.nonce);
+            tracked.boosts.push(offer.boost());
+            tracked.boost_spent = tracked.boost_spent.saturating_add(offer.cost);
+        });
+    }
+
+    /// Record a partial extraction that made it on chain.
+    fn track_extract(&self, action: &Action, wallet: &WalletState, result: &ActionResult) {
+        if !result.is_success() {
+            return;
+        }
+        let (Ok(Some(amount)), Ok(state)) =
+            (Self::parse_extract_amount(&action.data), Self::parse_state(wallet))
+        else {
+            return;
+        };
+        let Some(position) = state.position else {
+            return;
+        };
+        self.update(wallet.address, |tracked| {
+            let extracted = match tracked.extracted {
+                Some((entered, extracted)) if entered == position.entry_timestamp => extracted,
+                _ => U256::ZERO,
+            };
+            tracked.extracted =
+                Some((position.entry_timestamp, extracted.saturating_add(amount)));
+        });
+    }
+
+    /// Check if GhostCore takes an amount to extract.
+    ///
+    /// Found out once, by calling `extract(amount)` from `player`: a revert
+    /// without a reason means GhostCore has no such function. Until it is
+    /// known, e.g. while the call fails otherwise, extractions are in full.
+    async fn supports_partial_extract(&self, player: Address, amount: U256) -> bool {
+        let known = *self.partial_extract.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(supported) = known {
+            return supported;
+        }
+        let call = TransactionRequest::new()
+            .from(player)
+            .to(self.contracts.ghost_core)
+            .data(self.contracts.encode_extract(Some(amount)));
+        let supported = match self.provider.call(&call).await {
+            Ok(_) => true,
+            Err(e) if reverted_without_reason(&e.to_string()) => {
+                warn!(
+                    error = %e,
+                    "GhostCore does not support partial extraction, extracting in full from now on"
+                );
+                false
+            }
+            Err(e) => {
+                debug!(error = %e, "Could not check for partial extraction, extracting in full");
+                return false;
+            }
+        };
+        *self.partial_extract.lock().unwrap_or_else(PoisonError::into_inner) = Some(supported);
+        supported
+    }
+
+    /// Turn a partial extraction into a full one if GhostCore cannot extract
+    /// in part.
+    async fn supported_extract(&self, action: Option<Action>, player: Address) -> Option<Action> {
+        let action = action?;
+        if action.id.as_str() != ACTION_EXTRACT {
+            return Some(action);
+        }
+        match Self::parse_extract_amount(&action.data) {
+            Ok(Some(amount)) if !self.supports_partial_extract(player, amount).await => {
+                Some(Action::new(ACTION_EXTRACT, "Extract"))
+            }
+            _ => Some(action),
+        }
+    }
+
+    /// Estimate the gas of an action's transaction, and the gas limit to
+    /// send it with.
+    ///
+    /// A transaction that would revert fails with the decoded
+    /// [revert](GhostnetError::Reverted), one estimated to need more than the
+    /// action's maximum with [`GhostnetError::GasCapExceeded`].
+    async fn estimate_gas_limit(
+        &self,
+        action: &Action,
+        from: Address,
+        to: Address,
+        data: &Bytes,
+        value: U256,
+    ) -> Result<(u64, u64)> {
+        let tx = TransactionRequest::new()
+            .from(from)
+            .to(to)
+            .data(data.clone())
+            .value(value);
+        let estimate = self.provider.estimate_gas(&tx).await.map_err(|e| match e.revert_reason() {
+            Some(reason) if reason.is_empty() => GhostnetError::Reverted("no reason".into()),
+            Some(reason) => GhostnetError::Reverted(reason),
+            None => GhostnetError::Provider(e),
+        })?;
+        let gas = &self.config.gas;
+        let limit = gas.gas_limit(action.id.as_str(), estimate).ok_or_else(|| {
+            GhostnetError::GasCapExceeded {
+                estimate,
+                max: gas.max_gas_for(action.id.as_str()),
+            }
+        })?;
+        Ok((estimate, limit))
+    }
+
+    /// Snapshot of what the plugin tracks for a wallet.
+    fn tracked(&self, player: Address) -> TrackedWallet {
+        self.wallets
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&player)
+            .cloned()
             .unwrap_or_default()
     }
 
+    /// Update what the plugin tracks for a wallet.
+    fn update<T>(&self, player: Address, f: impl FnOnce(&mut TrackedWallet) -> T) -> T {
+        f(self
+            .wallets
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(player)
+            .or_default())
+    }
+
+    /// Merge what the plugin tracks into a wallet's GHOSTNET state.
+    ///
+    /// Boosts the plugin applied count as active until the state read from
+    /// chain shows them, so a grant is never applied on top of one in flight.
+    fn with_tracked(mut state: GhostnetState, tracked: TrackedWallet) -> GhostnetState {
+        if let Some(position) = state.position.as_mut() {
+            for boost in tracked.boosts {
+                if !position.active_boosts.contains(&boost) {
+                    position.active_boosts.push(boost);
+                }
+            }
+        }
+        if let Some((entry_timestamp, extracted)) = tracked.extracted
+            && state
+                .position
+                .as_ref()
+                .is_some_and(|position| position.entry_timestamp == entry_timestamp)
+        {
+            state.extracted = extracted;
+        }
+        state.pnl = tracked.pnl;
+        state.boost_offers = tracked.boost_offers;
+        state.boost_spent = tracked.boost_spent;
+        state
+    }
+
+    /// Parse the boost grant of an `apply_boost` action.
+    fn parse_offer(data: &serde_json::Value) -> Result<BoostOffer> {
+        serde_json::from_value(data["offer"].clone())
+            .map_err(|e| GhostnetError::InvalidActionData(format!("invalid offer: {e}")))
+    }
+
+    /// Parse GHOSTNET state from wallet plugin state.
+    ///
+    /// A wallet without state starts from the default.
+    fn parse_state(wallet: &WalletState) -> fleet_core::Result<GhostnetState> {
+        Ok(wallet
+            .get_plugin_state::<GhostnetState>(PLUGIN_ID)?
+            .unwrap_or_default())
+    }
+
     /// Build transaction for an action.
     fn build_tx(&self, action: &Action, _wallet: &WalletState) -> Result<(Address, Bytes, U256)> {
         match action.id.as_str() {
@@ -126,13 +1030,24 @@ impl<P: ChainProvider> GhostnetPlugin<P> {
                 Ok((self.contracts.ghost_core, calldata, U256::ZERO))
             }
             ACTION_EXTRACT => {
-                let calldata = self.contracts.encode_extract();
+                let amount = Self::parse_extract_amount(&action.data)?;
+                let calldata = self.contracts.encode_extract(amount);
                 Ok((self.contracts.ghost_core, calldata, U256::ZERO))
             }
             ACTION_CLAIM_REWARDS => {
                 let calldata = self.contracts.encode_claim_rewards();
                 Ok((self.contracts.ghost_core, calldata, U256::ZERO))
             }
+            ACTION_APPROVE => {
+                let amount = Self::parse_amount(&action.data, "amount")?;
+                let calldata = self.contracts.encode_approve(self.contracts.ghost_core, amount);
+                Ok((self.contracts.data_token, calldata, U256::ZERO))
+            }
+            ACTION_APPLY_BOOST => {
+                let offer = Self::parse_offer(&action.data)?;
+                let calldata = self.contracts.encode_apply_boost(&offer);
+                Ok((self.contracts.ghost_core, calldata, U256::ZERO))
+            }
             ACTION_HASHCRASH_BET => {
                 let amount = Self::parse_amount(&action.data, "amount")?;
                 let target = action.data["target_multiplier"]
@@ -147,6 +1062,23 @@ impl<P: ChainProvider> GhostnetPlugin<P> {
                 let calldata = self.contracts.encode_hashcrash_bet(amount, target_u16);
                 Ok((self.contracts.hash_crash, calldata, U256::ZERO))
             }
+            ACTION_ARCADE_PLAY => {
+                let amount = Self::parse_amount(&action.data, "amount")?;
+                let game_id = action.data["game_id"]
+                    .as_u64()
+                    .ok_or_else(|| GhostnetError::InvalidActionData("missing game_id".into()))?;
+                let calldata = self.contracts.encode_arcade_play(game_id, amount);
+                Ok((self.contracts.arcade_core, calldata, U256::ZERO))
+            }
+            ACTION_DEAD_POOL_BET => {
+                let dead_pool = self
+                    .contracts
+                    .dead_pool
+                    .ok_or_else(|| GhostnetError::InvalidConfig("no DeadPool address".into()))?;
+                let (round_id, over, amount) = Self::parse_dead_pool_bet(&action.data)?;
+                let calldata = self.contracts.encode_dead_pool_bet(round_id, over, amount);
+                Ok((dead_pool, calldata, U256::ZERO))
+            }
             _ => Err(GhostnetError::InvalidActionData(format!(
                 "unknown action: {}",
                 action.id
@@ -154,6 +1086,81 @@ impl<P: ChainProvider> GhostnetPlugin<P> {
         }
     }
 
+    /// Plugin-specific payload of an action result: the action's parameters
+    /// (amount, level, ...) plus the transaction that was built.
+    fn tx_detail(
+        action: &Action,
+        to: Address,
+        value: U256,
+        calldata_len: usize,
+        nonce: u64,
+    ) -> serde_json::Value {
+        let mut detail = match &action.data {
+            serde_json::Value::Object(params) => params.clone(),
+            _ => serde_json::Map::new(),
+        };
+        detail.insert("to".into(), serde_json::json!(to));
+        detail.insert("value".into(), serde_json::json!(value.to_string()));
+        detail.insert("calldata_len".into(), serde_json::json!(calldata_len));
+        detail.insert("nonce".into(), serde_json::json!(nonce));
+        serde_json::Value::Object(detail)
+    }
+
+    /// Drop a candidate action that is still on cooldown.
+    fn off_cooldown(action: Option<Action>, context: &PluginContext<'_>) -> Option<Action> {
+        action.filter(|action| {
+            let on_cooldown = context.is_on_cooldown(action.id.as_str());
+            if on_cooldown {
+                debug!(action = %action.id, "Skipping action on cooldown");
+            }
+            !on_cooldown
+        })
+    }
+
+    /// Check if a warmup step has to wait for a system reset: a GhostCore
+    /// step on a position not known since the reset, or a stake close to it.
+    fn waits_for_reset(
+        &self,
+        step: &Action,
+        state: &GhostnetState,
+        predates_reset: bool,
+        context: &PluginContext<'_>,
+    ) -> bool {
+        let now = u64::try_from(context.now.timestamp()).unwrap_or_default();
+        match step.id.as_str() {
+            ACTION_JACK_IN | ACTION_ADD_STAKE => {
+                predates_reset || state.is_reset_near(now, self.config.behavior.reset_danger_secs)
+            }
+            ACTION_EXTRACT | ACTION_CLAIM_REWARDS => predates_reset,
+            _ => false,
+        }
+    }
+
+    /// Update GhostCore's DATA allowance for a mined approval or stake.
+    ///
+    /// Returns whether the allowance changed.
+    fn track_allowance(state: &mut GhostnetState, action: &Action) -> bool {
+        let Ok(amount) = Self::parse_amount(&action.data, "amount") else {
+            return false;
+        };
+        let allowance = match action.id.as_str() {
+            ACTION_APPROVE => amount,
+            ACTION_JACK_IN | ACTION_ADD_STAKE => state.ghost_core_allowance.saturating_sub(amount),
+            _ => return false,
+        };
+        let changed = allowance != state.ghost_core_allowance;
+        state.ghost_core_allowance = allowance;
+        changed
+    }
+
+    /// Parse the amount of a partial extraction, `None` for a full one.
+    fn parse_extract_amount(data: &serde_json::Value) -> Result<Option<U256>> {
+        if data.get("amount").is_none() {
+            return Ok(None);
+        }
+        Self::parse_amount(data, "amount").map(Some)
+    }
+
     /// Parse amount from action data.
     fn parse_amount(data: &serde_json::Value, field: &str) -> Result<U256> {
         data[field]
@@ -163,6 +1170,17 @@ impl<P: ChainProvider> GhostnetPlugin<P> {
             .map_err(|_| GhostnetError::InvalidActionData(format!("invalid {field}")))
     }
 
+    /// Parse the round, side and amount of a DeadPool bet.
+    fn parse_dead_pool_bet(data: &serde_json::Value) -> Result<(u64, bool, U256)> {
+        let round_id = data["round_id"]
+            .as_u64()
+            .ok_or_else(|| GhostnetError::InvalidActionData("missing round_id".into()))?;
+        let over = data["is_over"]
+            .as_bool()
+            .ok_or_else(|| GhostnetError::InvalidActionData("missing is_over".into()))?;
+        Ok((round_id, over, Self::parse_amount(data, "amount")?))
+    }
+
     /// Parse level from action data.
     fn parse_level(data: &serde_json::Value) -> Result<u8> {
         data["level"]
@@ -172,6 +1190,12 @@ impl<P: ChainProvider> GhostnetPlugin<P> {
     }
 }
 
+/// Check if an error reports a call that reverted without a reason, as calls
+/// of functions a contract does not have do.
+fn reverted_without_reason(error: &str) -> bool {
+    revert_reason(error).is_some_and(|reason| reason.is_empty() || reason == "data: \"0x\"")
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // ACTION PLUGIN IMPLEMENTATION
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -180,7 +1204,7 @@ impl<P: ChainProvider> GhostnetPlugin<P> {
 impl<P: ChainProvider> ActionPlugin for GhostnetPlugin<P> {
     #[allow(clippy::unnecessary_literal_bound)] // Trait signature defines `&str`
     fn id(&self) -> &str {
-        "ghostnet"
+        PLUGIN_ID
     }
 
     #[allow(clippy::unnecessary_literal_bound)] // Trait signature defines `&str`
@@ -189,13 +1213,129 @@ impl<P: ChainProvider> ActionPlugin for GhostnetPlugin<P> {
     }
 
     fn available_actions(&self) -> Vec<ActionId> {
-        vec![
-            ActionId::new(ACTION_JACK_IN),
-            ActionId::new(ACTION_ADD_STAKE),
-            ActionId::new(ACTION_EXTRACT),
-            ActionId::new(ACTION_CLAIM_REWARDS),
-            ActionId::new(ACTION_HASHCRASH_BET),
-        ]
+        ACTIONS.into_iter().map(ActionId::new).collect()
+    }
+
+    fn default_cooldown(&self, action: &ActionId) -> Option<chrono::Duration> {
+        match action.as_str() {
+            ACTION_JACK_IN | ACTION_ADD_STAKE | ACTION_APPLY_BOOST => {
+                Some(chrono::Duration::hours(1))
+            }
+            ACTION_CLAIM_REWARDS => Some(chrono::Duration::hours(4)),
+            ACTION_HASHCRASH_BET | ACTION_ARCADE_PLAY | ACTION_DEAD_POOL_BET => {
+                Some(chrono::Duration::minutes(15))
+            }
+            // Never hold a wallet back from exiting
+            _ => None,
+        }
+    }
+
+    fn estimate_spend(&self, action: &Action) -> Spend {
+        // Stakes and bets carry their DATA amount, extractions bring theirs
+        // in and approvals only allow the stake; gas is only known once mined
+        if matches!(action.id.as_str(), ACTION_EXTRACT | ACTION_APPROVE) {
+            return Spend::action();
+        }
+        Self::parse_amount(&action.data, "amount")
+            .map_or_else(|_| Spend::action(), |amount| Spend::action().with_data(amount))
+    }
+
+    fn requirements(&self, action: &ActionId) -> ActionRequirements {
+        let data_token = self.config.data_token;
+        let behavior = &self.config.behavior;
+        // Every action is a transaction, so needs gas money
+        let gas = ActionRequirements::none().with_native_balance(U256::from(1));
+        // Stakes and bets keep the reserve and put in at least one wei
+        let stake = U256::from(behavior.data_reserve).saturating_add(U256::from(1));
+        match action.as_str() {
+            ACTION_JACK_IN => gas.with_token_balance(
+                data_token,
+                U256::from(behavior.min_entry_balance).max(stake),
+            ),
+            ACTION_ADD_STAKE => gas.with_token_balance(data_token, stake).with_position(),
+            ACTION_EXTRACT | ACTION_CLAIM_REWARDS | ACTION_APPLY_BOOST => gas.with_position(),
+            // Approvals are only ever decided for a stake
+            ACTION_APPROVE | ACTION_HASHCRASH_BET | ACTION_ARCADE_PLAY | ACTION_DEAD_POOL_BET => {
+                gas.with_token_balance(data_token, stake)
+            }
+            _ => ActionRequirements::none(),
+        }
+    }
+
+    /// Uses only the cached balances and state, and what the plugin tracks.
+    ///
+    /// A stale DATA balance or unsettled HashCrash rounds always let the
+    /// wallet through, as deciding refreshes or settles them.
+    fn can_possibly_act(&self, wallet: &WalletState, now: chrono::DateTime<chrono::Utc>) -> bool {
+        let max_age = self.config.behavior.max_balance_age();
+        if wallet.is_balance_stale_at(self.config.data_token, max_age, now)
+            || !self.pnl(wallet.address).pending_rounds().is_empty()
+        {
+            return true;
+        }
+        if wallet.is_retiring() {
+            return self.has_open_positions(wallet);
+        }
+        // Invalid state surfaces as an error when deciding
+        let Ok(state) = Self::parse_state(wallet) else {
+            return true;
+        };
+        let behavior = &self.config.behavior;
+        self.available_actions().iter().any(|action| {
+            let enabled = match action.as_str() {
+                ACTION_HASHCRASH_BET => behavior.plays_hashcrash,
+                ACTION_ARCADE_PLAY => behavior.plays_arcade,
+                ACTION_DEAD_POOL_BET => {
+                    behavior.plays_dead_pool && self.contracts.dead_pool.is_some()
+                }
+                ACTION_APPROVE => behavior.approve_before_stake,
+                _ => true,
+            };
+            let requirements = self.requirements(action);
+            enabled
+                && requirements.balances_met_by(wallet)
+                && (!requirements.requires_position || state.has_active_position())
+        })
+    }
+
+    fn is_exit_action(&self, action: &ActionId) -> bool {
+        matches!(action.as_str(), ACTION_EXTRACT | ACTION_CLAIM_REWARDS)
+    }
+
+    /// An alive position or unsettled HashCrash bets. Invalid state counts as
+    /// open, so a wallet is never retired on state that could not be read.
+    fn has_open_positions(&self, wallet: &WalletState) -> bool {
+        !self.pnl(wallet.address).pending_rounds().is_empty()
+            || Self::parse_state(wallet).map_or(true, |state| state.has_active_position())
+    }
+
+    /// DATA staked and taken out as the wallet's receipts show, HashCrash
+    /// winnings and losses, and the open position and bets.
+    fn activity(&self, wallet: &WalletState) -> Option<PluginActivity> {
+        let tracked = self.tracked(wallet.address);
+        let mut positions: Vec<String> = Self::parse_state(wallet)
+            .ok()
+            .and_then(|state| state.position)
+            .filter(|position| position.alive)
+            .map(|position| format!("{}: {} wei", position.level, position.amount))
+            .into_iter()
+            .collect();
+        positions.extend(
+            tracked
+                .pnl
+                .bets
+                .iter()
+                .filter(|bet| bet.outcome == BetOutcome::Pending)
+                .map(|bet| format!("HashCrash round {}: {} wei", bet.round_id, bet.amount)),
+        );
+
+        Some(PluginActivity {
+            staked: tracked.flows.staked,
+            extracted: tracked.flows.extracted,
+            won: tracked.pnl.total_won,
+            lost: tracked.pnl.total_lost.saturating_add(tracked.flows.culled),
+            positions,
+        })
     }
 
     #[instrument(skip(self, wallet, context), fields(wallet_id = %wallet.id))]
@@ -205,24 +1345,106 @@ impl<P: ChainProvider> ActionPlugin for GhostnetPlugin<P> {
         profile: &BehaviorProfile,
         context: &mut PluginContext<'_>,
     ) -> fleet_core::Result<Option<Action>> {
-        let state = Self::parse_state(wallet);
+        // Stakes and bets are sized from the DATA balance, so never decide on
+        // one older than the configured threshold
+        let data_token = self.config.data_token;
+        let max_age = self.config.behavior.max_balance_age();
+        if wallet.is_balance_stale_at(data_token, max_age, context.now) {
+            debug!(token = %data_token, "DATA balance is stale, requesting refresh");
+            return Ok(Some(Action::refresh_balances(&[data_token])));
+        }
 
-        // Try GhostCore actions first (higher priority)
-        if let Some(action) =
-            GhostCoreDecider::decide(&state, profile, &self.config.behavior, context)
+        // Settle finished rounds first so bet sizing sees the latest streak
+        if !self.pnl(wallet.address).pending_rounds().is_empty()
+            && let Err(e) = self.reconcile_wallet(wallet.address).await
         {
-            debug!(action = %action.id, "GhostCore action decided");
-            return Ok(Some(action));
+            warn!(error = %e, "Failed to reconcile HashCrash bets");
+        }
+
+        let mut state =
+            Self::with_tracked(Self::parse_state(wallet)?, self.tracked(wallet.address));
+        state.data_balance = wallet.token_balance(data_token);
+        state.reset_timer = self.reset_timer(context.now).await;
+        state.transfer_tax = self.transfer_tax(wallet.address, context.now).await;
+
+        // A reset settled a penalty on the position, and may have ended it
+        let predates_reset = state.predates_reset();
+        if predates_reset {
+            info!(
+                epoch = ?state.reset_epoch,
+                "Position predates the last system reset, holding until it is read again"
+            );
+            state.forget_position();
+            self.update(wallet.address, |tracked| tracked.boosts.clear());
+        }
+        if state.has_active_position() {
+            state.exit_toll = self.exit_toll(context.now).await;
+        }
+
+        // Retiring wallets only get out of their position
+        if wallet.is_retiring() {
+            let exit = RetirementDecider::decide(&state).filter(|_| !predates_reset);
+            return Ok(Self::off_cooldown(exit, context));
+        }
+
+        // Warming wallets only take the small steps of their plan
+        if let Some(plan) = wallet.warmup_at(context.now) {
+            let step = WarmupDecider::decide(&state, plan, profile, &self.config.behavior, context)
+                .filter(|step| !self.waits_for_reset(step, &state, predates_reset, context));
+            return Ok(Self::off_cooldown(step, context)
+                .map(|step| GhostCoreDecider::approved(step, &state, &self.config.behavior)));
+        }
+
+        // Try GhostCore actions first (higher priority), then boosts for the
+        // position, unless the position is not known since a reset
+        if !predates_reset {
+            let ghost_core =
+                GhostCoreDecider::decide(&state, profile, &self.config.behavior, context);
+            let ghost_core = self.supported_extract(ghost_core, wallet.address).await;
+            if let Some(action) = Self::off_cooldown(ghost_core, context) {
+                debug!(action = %action.id, "GhostCore action decided");
+                return Ok(Some(GhostCoreDecider::approved(action, &state, &self.config.behavior)));
+            }
+
+            let boost = BoostDecider::decide(&state, profile, &self.config.behavior, context);
+            if let Some(action) = Self::off_cooldown(boost, context) {
+                debug!(action = %action.id, "Boost action decided");
+                return Ok(Some(action));
+            }
         }
 
         // Try HashCrash actions
-        if let Some(action) =
-            HashCrashDecider::decide(&state, profile, &self.config.behavior, context)
-        {
+        let hashcrash = HashCrashDecider::decide(&state, profile, &self.config.behavior, context);
+        if let Some(action) = Self::off_cooldown(hashcrash, context) {
             debug!(action = %action.id, "HashCrash action decided");
             return Ok(Some(action));
         }
 
+        // Then DeadPool rounds, if a DeadPool is configured
+        if self.contracts.dead_pool.is_some()
+            && self.config.behavior.plays_dead_pool
+            && !context.is_on_cooldown(ACTION_DEAD_POOL_BET)
+        {
+            let rounds = self.open_rounds(wallet.address, context.now).await;
+            let bet =
+                DeadPoolDecider::decide(&state, &rounds, profile, &self.config.behavior, context);
+            if let Some(action) = bet {
+                debug!(action = %action.id, "DeadPool action decided");
+                return Ok(Some(action));
+            }
+        }
+
+        // Then the other arcade games, if ArcadeCore lists any
+        if self.config.behavior.plays_arcade && !context.is_on_cooldown(ACTION_ARCADE_PLAY) {
+            let games = self.playable_games().await;
+            let arcade =
+                ArcadeDecider::decide(&state, &games, profile, &self.config.behavior, context);
+            if let Some(action) = arcade {
+                debug!(action = %action.id, "Arcade action decided");
+                return Ok(Some(action));
+            }
+        }
+
         Ok(None)
     }
 
@@ -237,11 +1459,38 @@ impl<P: ChainProvider> ActionPlugin for GhostnetPlugin<P> {
         nonce: u64,
     ) -> fleet_core::Result<ActionResult> {
         info!(action = %action.name, "Executing GHOSTNET action");
+        let started = std::time::Instant::now();
 
         // Build transaction
         let (to, data, value) = self
             .build_tx(action, wallet)
-            .map_err(|e| fleet_core::FleetError::PluginExecution(e.to_string()))?;
+            .map_err(fleet_core::FleetError::from)?;
+
+        // Bet on DeadPool only at odds close to those decided on
+        if action.id.as_str() == ACTION_DEAD_POOL_BET
+            && let Some(reason) = self
+                .dead_pool_odds_moved(action)
+                .await
+                .map_err(fleet_core::FleetError::from)?
+        {
+            info!(reason, "DeadPool odds moved, not betting");
+            return Ok(ActionResult::skipped(reason).with_duration(started.elapsed()));
+        }
+
+        // Estimate gas first: a transaction that would revert, or needs more
+        // gas than the action may use, is not worth sending
+        let (estimate, gas_limit) =
+            match self.estimate_gas_limit(action, wallet.address, to, &data, value).await {
+                Ok(gas) => gas,
+                Err(e @ (GhostnetError::Reverted(_) | GhostnetError::GasCapExceeded { .. })) => {
+                    info!(error = %e, "Gas estimation refused the action, not sending it");
+                    return Ok(ActionResult::skipped(e.to_string())
+                        .with_duration(started.elapsed()));
+                }
+                Err(e) => return Err(e.into()),
+            };
+        let mut detail = Self::tx_detail(action, to, value, data.len(), nonce);
+        detail["gas_limit"] = serde_json::json!(gas_limit);
 
         // TODO: Sign and send transaction using wallet's signer
         // For now, we just return a placeholder error indicating the transaction
@@ -249,41 +1498,161 @@ impl<P: ChainProvider> ActionPlugin for GhostnetPlugin<P> {
 
         // In production, this would:
         // 1. Check and ensure token approval if needed
-        // 2. Build the full transaction with gas estimation
-        // 3. Sign with the wallet's signer
-        // 4. Submit via provider.send_raw_transaction()
-        // 5. Wait for receipt
+        // 2. Sign with the wallet's signer, with the estimated gas limit
+        // 3. Submit it, through the realtime API where the chain supports it
+        //    as the engine's own submissions do
+        // 4. Wait for receipt
 
         warn!(
             to = %to,
             data_len = data.len(),
             value = %value,
             nonce = nonce,
+            gas_limit,
             "Transaction built but not submitted (signing not implemented)"
         );
 
         // Return failure for now - the orchestrator needs to handle signing
-        Ok(ActionResult::failure(
+        let result = ActionResult::failure(
             "Transaction signing not implemented in plugin - use build_transaction()",
-        ))
+        )
+        .with_gas_estimate(estimate)
+        .with_detail(detail)
+        .with_duration(started.elapsed());
+
+        match action.id.as_str() {
+            ACTION_HASHCRASH_BET => Ok(self.track_bet(action, wallet, result)),
+            ACTION_APPLY_BOOST => {
+                self.track_boost(action, wallet, &result);
+                Ok(result)
+            }
+            ACTION_EXTRACT => {
+                self.track_extract(action, wallet, &result);
+                Ok(result)
+            }
+            ACTION_DEAD_POOL_BET => {
+                self.track_dead_pool_bet(action, wallet, &result);
+                Ok(result)
+            }
+            _ => Ok(result),
+        }
     }
 
-    #[instrument(skip(self), fields(address = %_address))]
-    async fn read_state(&self, _address: Address) -> fleet_core::Result<serde_json::Value> {
+    #[instrument(skip(self), fields(address = %address))]
+    async fn read_state(&self, address: Address) -> fleet_core::Result<serde_json::Value> {
         debug!("Reading GHOSTNET state");
 
         // In production, this would call the contract view functions:
         // - GhostCore.getPosition(address)
         // - GhostCore.getPendingRewards(address)
         // - GhostCore.getEffectiveDeathRate(address)
+        // - GhostCore.getSystemReset() (exit toll, see decode_exit_toll)
+        // - GhostCore.getCullingRisk(address) (see decode_culling_risk)
         // - DataToken.balanceOf(address)
         // - DataToken.allowance(address, ghost_core)
         // - HashCrash.getCurrentRound()
 
-        // For now, return empty state
-        let state = GhostnetState::default();
+        // - GhostCore.getActiveBoosts(address)
+
+        // For now, return empty state apart from what the plugin tracks, read
+        // in the current reset epoch
+        let mut state = Self::with_tracked(GhostnetState::default(), self.tracked(address));
+        state.reset_timer = self.reset_timer(chrono::Utc::now()).await;
+        state.reset_epoch = state.reset_timer.map(|timer| timer.epoch);
+
+        Ok(state.to_value()?)
+    }
+
+    fn state_schema_version(&self) -> u32 {
+        GhostnetState::SCHEMA_VERSION
+    }
+
+    fn state_from_receipt(
+        &self,
+        action: &Action,
+        wallet: &WalletState,
+        receipt: &TransactionReceipt,
+    ) -> Option<serde_json::Value> {
+        let events = parse_ghostnet_events(receipt, self.contracts.ghost_core);
+        self.update(wallet.address, |tracked| tracked.flows.record(wallet.address, &events));
+        let mut state = Self::parse_state(wallet).ok()?;
+        let now = u64::try_from(chrono::Utc::now().timestamp()).unwrap_or_default();
+        let applied = apply_events(&mut state, wallet.address, &events, now);
+        let allowed = receipt.success && Self::track_allowance(&mut state, action);
+        if !applied && !allowed {
+            return None;
+        }
+        debug!(events = events.len(), "Updated GHOSTNET state from receipt");
+
+        let state = Self::with_tracked(state, self.tracked(wallet.address));
+        state.to_value().ok()
+    }
+
+    /// Stakes and exits from the receipt's GhostCore events, wagers of bets
+    /// and games that went through, and DATA paid to the wallet by a reward
+    /// claim. Cascade rewards are paid out with an exit or a claim, so they
+    /// show up there.
+    fn data_flows(
+        &self,
+        action: &Action,
+        wallet: &WalletState,
+        receipt: &TransactionReceipt,
+    ) -> Vec<DataFlow> {
+        let player = wallet.address;
+        let mut flows = Vec::new();
+        let events = parse_ghostnet_events(receipt, self.contracts.ghost_core);
+        for event in events.iter().filter(|event| event.user() == player) {
+            match event {
+                GhostnetEvent::JackedIn(e) => flows.push(DataFlow::Staked {
+                    amount: e.amount,
+                    opened: true,
+                }),
+                GhostnetEvent::StakeAdded(e) => flows.push(DataFlow::Staked {
+                    amount: e.amount,
+                    opened: false,
+                }),
+                GhostnetEvent::Extracted(e) => flows.push(DataFlow::Exited {
+                    principal: Self::parse_extract_amount(&action.data).ok().flatten(),
+                    proceeds: e.amount.saturating_add(e.rewards),
+                }),
+                GhostnetEvent::PositionCulled(e) => flows.push(DataFlow::Exited {
+                    principal: None,
+                    proceeds: e.returnedAmount,
+                }),
+                GhostnetEvent::BoostApplied(_) => {}
+            }
+        }
+        match action.id.as_str() {
+            ACTION_HASHCRASH_BET | ACTION_ARCADE_PLAY | ACTION_DEAD_POOL_BET => {
+                if let Ok(amount) = Self::parse_amount(&action.data, "amount") {
+                    flows.push(DataFlow::Wagered { amount });
+                }
+            }
+            ACTION_CLAIM_REWARDS => {
+                let amount = data_received(receipt, self.contracts.data_token, player);
+                if !amount.is_zero() {
+                    flows.push(DataFlow::Received { amount });
+                }
+            }
+            _ => {}
+        }
+        flows
+    }
 
-        serde_json::to_value(state).map_err(fleet_core::FleetError::Serialization)
+    /// Payouts of won and refunded HashCrash bets, by round. Arcade games
+    /// pay out in sessions the plugin does not track, so their winnings are
+    /// not counted.
+    fn settled_flows(&self, wallet: &WalletState) -> Vec<(String, DataFlow)> {
+        self.pnl(wallet.address)
+            .bets
+            .iter()
+            .filter(|bet| matches!(bet.outcome, BetOutcome::Won | BetOutcome::Refunded))
+            .filter(|bet| !bet.payout.is_zero())
+            .map(|bet| {
+                let id = format!("hashcrash:{}", bet.round_id);
+                (id, DataFlow::Received { amount: bet.payout })
+            })
+            .collect()
     }
 
     async fn build_transaction(
@@ -294,7 +1663,7 @@ impl<P: ChainProvider> ActionPlugin for GhostnetPlugin<P> {
     ) -> fleet_core::Result<Bytes> {
         let (to, data, value) = self
             .build_tx(action, wallet)
-            .map_err(|e| fleet_core::FleetError::PluginExecution(e.to_string()))?;
+            .map_err(fleet_core::FleetError::from)?;
 
         // Return just the calldata - the orchestrator will build the full transaction
         debug!(
@@ -313,9 +1682,21 @@ impl<P: ChainProvider> ActionPlugin for GhostnetPlugin<P> {
 // ═══════════════════════════════════════════════════════════════════════════════
 
 #[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
 mod tests {
     use super::*;
+    use crate::config::{BehaviorSettings, ExtractStrategy};
+    use crate::contracts::RoundView;
+    use crate::state::{BoostType, Position};
+    use alloy::primitives::{B256, Bytes, I256};
+    use alloy::sol_types::{SolCall, SolEvent};
     use evm_provider::mock::MockProvider;
+    use ghostnet_abi::{data_token, ghost_core, hash_crash};
+    use evm_provider::ProviderError;
+    use fleet_core::plugins::ActionStatus;
+    use fleet_core::wallet::{TrackedBalance, WarmupSettings, WarmupStepKind};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
 
     fn test_plugin() -> GhostnetPlugin<MockProvider> {
         let config = GhostnetConfig::testnet();
@@ -338,7 +1719,15 @@ mod tests {
         assert!(actions.iter().any(|a| a.as_str() == ACTION_JACK_IN));
         assert!(actions.iter().any(|a| a.as_str() == ACTION_ADD_STAKE));
         assert!(actions.iter().any(|a| a.as_str() == ACTION_EXTRACT));
+        assert!(actions.iter().any(|a| a.as_str() == ACTION_APPLY_BOOST));
         assert!(actions.iter().any(|a| a.as_str() == ACTION_HASHCRASH_BET));
+        assert!(actions.iter().any(|a| a.as_str() == ACTION_ARCADE_PLAY));
+        assert!(actions.iter().any(|a| a.as_str() == ACTION_DEAD_POOL_BET));
+
+        // Every action is well-formed and the plugin's own
+        let mut registry = fleet_core::plugins::PluginRegistry::new();
+        registry.register(Arc::new(plugin)).unwrap();
+        assert!(actions.iter().all(|a| a.plugin_part() == "ghostnet"));
     }
 
     #[test]
@@ -420,12 +1809,107 @@ mod tests {
         assert!(result.is_ok());
 
         let action_result = result.unwrap();
-        assert!(!action_result.success, "execute_action should return failure");
+        assert_eq!(action_result.status, ActionStatus::Dropped, "no tx should make it on chain");
+        let error = action_result.error.as_ref().expect("should carry an error");
         assert!(
-            action_result.error.as_ref().is_some_and(|e| e.contains("signing not implemented")),
+            error.message.contains("signing not implemented"),
             "error should mention signing not implemented"
         );
+        assert!(!action_result.is_retryable(), "retrying will not help");
         assert!(action_result.tx_hash.is_none(), "no tx should be sent");
+        assert!(action_result.duration_ms.is_some());
+
+        // The detail carries the action parameters and the built transaction
+        let detail = &action_result.detail;
+        assert_eq!(detail["level"], 3);
+        assert_eq!(detail["amount"], "1000000000000000000");
+        assert_eq!(detail["to"], serde_json::json!(plugin.contracts.ghost_core));
+        assert_eq!(detail["nonce"], 0);
+        // Sent with the estimate plus the margin
+        assert_eq!(action_result.gas_estimate, Some(100_000));
+        assert_eq!(detail["gas_limit"], 120_000);
+    }
+
+    fn jack_in() -> Action {
+        Action::with_data(
+            ACTION_JACK_IN,
+            "Jack In",
+            serde_json::json!({ "amount": "1000000000000000000", "level": 3 }),
+        )
+    }
+
+    async fn execute_estimated(
+        chain: &Arc<evm_provider::mock_chain::MockChainProvider>,
+        estimate: evm_provider::Result<u64>,
+    ) -> fleet_core::Result<ActionResult> {
+        use evm_provider::mock_chain::{Method, Response};
+
+        let mut config = GhostnetConfig::testnet();
+        config.gas.action_max_gas.insert(ACTION_JACK_IN.into(), 1_000_000);
+        let plugin = GhostnetPlugin::new(config, Arc::clone(chain));
+        chain.push_response(Method::EstimateGas, estimate.map(Response::Gas));
+        let wallet = WalletState::new("test".into(), Address::repeat_byte(0x11));
+        plugin.execute_action(&jack_in(), &wallet, 0).await
+    }
+
+    #[tokio::test]
+    async fn estimates_gas_before_sending() {
+        use evm_provider::mock_chain::{MockChainProvider, RecordedCall};
+
+        let chain = Arc::new(MockChainProvider::new());
+        let result = execute_estimated(&chain, Ok(200_000)).await.unwrap();
+
+        assert_eq!(result.gas_estimate, Some(200_000));
+        assert_eq!(result.detail["gas_limit"], 240_000);
+        let calls = chain.calls();
+        let [RecordedCall::EstimateGas(tx)] = calls.as_slice() else {
+            unreachable!("should estimate once: {calls:?}");
+        };
+        assert_eq!(tx.from, Some(Address::repeat_byte(0x11)));
+        assert_eq!(tx.to, Some(GhostnetConfig::testnet().ghost_core));
+        assert!(tx.data.is_some());
+
+        // The margin is capped at the action's maximum
+        let result = execute_estimated(&chain, Ok(900_000)).await.unwrap();
+        assert_eq!(result.detail["gas_limit"], 1_000_000);
+    }
+
+    #[tokio::test]
+    async fn reverting_estimates_skip_the_action() {
+        use evm_provider::mock_chain::MockChainProvider;
+
+        let chain = Arc::new(MockChainProvider::new());
+        let reverted = ProviderError::rpc(3, "execution reverted: PositionAlreadyExists()");
+        let result = execute_estimated(&chain, Err(reverted)).await.unwrap();
+
+        assert_eq!(result.status, ActionStatus::Skipped, "deciding not to act is no failure");
+        assert!(result.error.is_none());
+        let reason = result.detail["reason"].as_str().unwrap();
+        assert!(reason.contains("PositionAlreadyExists()"), "{reason}");
+        assert!(chain.sent_transactions().is_empty());
+    }
+
+    #[tokio::test]
+    async fn estimates_over_the_cap_skip_the_action() {
+        use evm_provider::mock_chain::MockChainProvider;
+
+        let chain = Arc::new(MockChainProvider::new());
+        let result = execute_estimated(&chain, Ok(30_000_000)).await.unwrap();
+
+        assert_eq!(result.status, ActionStatus::Skipped);
+        let reason = result.detail["reason"].as_str().unwrap();
+        assert!(reason.contains("exceeds the cap of 1000000"), "{reason}");
+    }
+
+    #[tokio::test]
+    async fn failed_estimates_are_errors() {
+        use evm_provider::mock_chain::MockChainProvider;
+
+        let chain = Arc::new(MockChainProvider::new());
+        let timeout = ProviderError::Timeout(std::time::Duration::from_secs(5));
+        let error = execute_estimated(&chain, Err(timeout)).await.unwrap_err();
+
+        assert_eq!(error.class(), fleet_core::ErrorClass::Transient);
     }
 
     #[tokio::test]
@@ -458,4 +1942,1166 @@ mod tests {
         let state = result.unwrap();
         assert!(state.is_object());
     }
+
+    #[tokio::test]
+    async fn read_state_typed_returns_current_schema() {
+        let plugin = test_plugin();
+
+        let state: GhostnetState = plugin.read_state_typed(Address::ZERO).await.unwrap();
+        assert_eq!(state.schema_version, GhostnetState::SCHEMA_VERSION);
+        assert!(state.position.is_none());
+    }
+
+    #[test]
+    fn default_cooldowns() {
+        let plugin = test_plugin();
+
+        let bet = plugin.default_cooldown(&ActionId::new(ACTION_HASHCRASH_BET));
+        assert_eq!(bet, Some(chrono::Duration::minutes(15)));
+        assert_eq!(plugin.default_cooldown(&ActionId::new(ACTION_EXTRACT)), None);
+    }
+
+    #[test]
+    fn estimates_data_spend() {
+        let plugin = test_plugin();
+        let bet = Action::with_data(
+            ACTION_HASHCRASH_BET,
+            "HashCrash Bet",
+            serde_json::json!({ "amount": "5000", "target_multiplier": 200 }),
+        );
+
+        assert_eq!(plugin.estimate_spend(&bet), Spend::action().with_data(U256::from(5000)));
+        let extract = Action::new(ACTION_EXTRACT, "Extract");
+        assert_eq!(plugin.estimate_spend(&extract), Spend::action());
+    }
+
+    #[test]
+    fn skips_candidates_on_cooldown() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let now = chrono::Utc::now();
+        let cooldowns = fleet_core::plugins::ActionCooldowns::new()
+            .with_cooldown(ACTION_HASHCRASH_BET, chrono::Duration::minutes(15))
+            .with_last_executed(ACTION_HASHCRASH_BET, now - chrono::Duration::minutes(5));
+        let context = PluginContext::new(now, &mut rng, &serde_json::Value::Null)
+            .with_cooldowns(cooldowns);
+
+        let bet = Action::new(ACTION_HASHCRASH_BET, "HashCrash Bet");
+        assert!(GhostnetPlugin::<MockProvider>::off_cooldown(Some(bet), &context).is_none());
+        let extract = Action::new(ACTION_EXTRACT, "Extract");
+        assert!(GhostnetPlugin::<MockProvider>::off_cooldown(Some(extract), &context).is_some());
+    }
+
+    #[tokio::test]
+    async fn stale_balance_requests_refresh() {
+        let plugin = test_plugin();
+        let data_token = plugin.config.data_token;
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut context =
+            PluginContext::new(chrono::Utc::now(), &mut rng, &serde_json::Value::Null);
+
+        // Never read
+        let mut wallet = WalletState::new("test".into(), Address::ZERO);
+        let action = plugin
+            .decide_action(&wallet, &BehaviorProfile::degen(), &mut context)
+            .await
+            .unwrap()
+            .expect("should request a refresh");
+        assert_eq!(action.refresh_tokens(), Some(vec![data_token]));
+
+        // Read too long ago
+        let max_age = plugin.config.behavior.max_balance_age();
+        wallet.set_tracked_balance(
+            data_token,
+            TrackedBalance::at(U256::MAX, 1, context.now - max_age * 2),
+        );
+        let action = plugin
+            .decide_action(&wallet, &BehaviorProfile::degen(), &mut context)
+            .await
+            .unwrap()
+            .expect("should request a refresh");
+        assert_eq!(action.refresh_tokens(), Some(vec![data_token]));
+    }
+
+    #[tokio::test]
+    async fn fresh_balance_sizes_from_wallet() {
+        let plugin = test_plugin();
+        let data_token = plugin.config.data_token;
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut context =
+            PluginContext::new(chrono::Utc::now(), &mut rng, &serde_json::Value::Null);
+
+        // A fresh balance below every minimum: nothing to do, and no refresh
+        let mut wallet = WalletState::new("test".into(), Address::ZERO);
+        wallet.set_token_balance(data_token, U256::from(1), 1);
+        let action = plugin
+            .decide_action(&wallet, &BehaviorProfile::degen(), &mut context)
+            .await
+            .unwrap();
+        assert!(action.is_none(), "unexpected action: {action:?}");
+    }
+
+    #[test]
+    fn prefilters_wallets_that_cannot_act() {
+        let plugin = test_plugin();
+        let data_token = plugin.config.data_token;
+        let now = chrono::Utc::now();
+
+        // Never read: deciding refreshes the balance
+        let mut wallet = WalletState::new("test".into(), Address::ZERO);
+        assert!(plugin.can_possibly_act(&wallet, now));
+
+        // Fresh and empty
+        wallet.set_token_balance(data_token, U256::ZERO, 1);
+        assert!(!plugin.can_possibly_act(&wallet, now));
+
+        // DATA without gas money
+        let entry = U256::from(plugin.config.behavior.min_entry_balance);
+        wallet.set_token_balance(data_token, entry, 1);
+        assert!(!plugin.can_possibly_act(&wallet, now));
+
+        wallet.native_balance = U256::from(1);
+        assert!(plugin.can_possibly_act(&wallet, now));
+    }
+
+    #[test]
+    fn positions_need_no_data() {
+        let plugin = test_plugin();
+        let extract = plugin.requirements(&ActionId::new(ACTION_EXTRACT));
+        assert!(extract.requires_position);
+        assert!(extract.min_token_balances.is_empty());
+
+        let jack_in = plugin.requirements(&ActionId::new(ACTION_JACK_IN));
+        assert!(!jack_in.requires_position);
+        assert_eq!(
+            jack_in.min_token_balances[&plugin.config.data_token],
+            U256::from(plugin.config.behavior.min_entry_balance)
+        );
+    }
+
+    fn set_reset_timer(provider: &MockProvider, ghost_core: Address, timer: ResetTimer) {
+        let reset = ghost_core::SystemReset {
+            deadline: timer.deadline,
+            lastDepositor: Address::ZERO,
+            lastDepositTime: 0,
+            epoch: U256::from(timer.epoch),
+            penaltyBps: timer.penalty_bps,
+        };
+        provider.register_call_response(
+            ghost_core,
+            ghost_core::getSystemResetCall::SELECTOR,
+            ghost_core::getSystemResetCall::abi_encode_returns(&reset).into(),
+        );
+    }
+
+    fn set_exit_toll(provider: &MockProvider, ghost_core: Address, penalty_bps: u16) {
+        let timer = ResetTimer {
+            penalty_bps,
+            ..ResetTimer::default()
+        };
+        set_reset_timer(provider, ghost_core, timer);
+    }
+
+    #[tokio::test]
+    async fn exit_toll_is_cached_for_its_ttl() {
+        let config = GhostnetConfig::testnet();
+        let ghost_core = config.ghost_core;
+        let ttl = config.behavior.exit_toll_ttl();
+        let provider = Arc::new(MockProvider::new());
+        set_exit_toll(&provider, ghost_core, 1000);
+        let plugin = GhostnetPlugin::new(config, Arc::clone(&provider));
+        let now = chrono::Utc::now();
+
+        assert_eq!(plugin.exit_toll(now).await, ExitToll { penalty_bps: 1000 });
+        set_exit_toll(&provider, ghost_core, 2000);
+        let cached = plugin.exit_toll(now + ttl - chrono::Duration::seconds(1)).await;
+        assert_eq!(cached, ExitToll { penalty_bps: 1000 });
+        assert_eq!(plugin.exit_toll(now + ttl).await, ExitToll { penalty_bps: 2000 });
+    }
+
+    #[tokio::test]
+    async fn unreadable_exit_toll_falls_back_to_the_default() {
+        let mut config = GhostnetConfig::testnet();
+        config.behavior.default_exit_toll_bps = 300;
+        let plugin = GhostnetPlugin::new(config, Arc::new(MockProvider::new()));
+
+        let toll = plugin.exit_toll(chrono::Utc::now()).await;
+        assert_eq!(toll, ExitToll { penalty_bps: 300 });
+    }
+
+    fn set_transfer_tax(
+        provider: &MockProvider,
+        data_token: Address,
+        rate_bps: u16,
+        excluded: bool,
+    ) {
+        provider.register_call_response(
+            data_token,
+            data_token::TAX_RATE_BPSCall::SELECTOR,
+            data_token::TAX_RATE_BPSCall::abi_encode_returns(&rate_bps).into(),
+        );
+        // Answered the same for every address
+        provider.register_call_response(
+            data_token,
+            data_token::isExcludedFromTaxCall::SELECTOR,
+            data_token::isExcludedFromTaxCall::abi_encode_returns(&excluded).into(),
+        );
+    }
+
+    #[tokio::test]
+    async fn excluded_wallets_are_untaxed() {
+        let config = GhostnetConfig::testnet();
+        let data_token = config.data_token;
+        let provider = Arc::new(MockProvider::new());
+        set_transfer_tax(&provider, data_token, 1000, true);
+        let plugin = GhostnetPlugin::new(config, Arc::clone(&provider));
+        let wallet = Address::repeat_byte(0xaa);
+        let now = chrono::Utc::now();
+
+        assert_eq!(plugin.transfer_tax(wallet, now).await, TransferTax::default());
+
+        // Not excluded, the wallet is taxed towards the contracts that are not
+        // either
+        set_transfer_tax(&provider, data_token, 1000, false);
+        let later = now + plugin.config.behavior.transfer_tax_ttl();
+        plugin
+            .lock_tax()
+            .excluded
+            .insert(plugin.contracts.ghost_core, (true, later));
+        let tax = plugin.transfer_tax(wallet, later).await;
+        assert_eq!(
+            tax,
+            TransferTax {
+                ghost_core_bps: 0,
+                arcade_core_bps: 1000,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn transfer_tax_is_cached_for_its_ttl() {
+        let config = GhostnetConfig::testnet();
+        let data_token = config.data_token;
+        let ttl = config.behavior.transfer_tax_ttl();
+        let provider = Arc::new(MockProvider::new());
+        set_transfer_tax(&provider, data_token, 1000, false);
+        let plugin = GhostnetPlugin::new(config, Arc::clone(&provider));
+        let wallet = Address::repeat_byte(0xaa);
+        let now = chrono::Utc::now();
+
+        assert_eq!(plugin.transfer_tax(wallet, now).await.ghost_core_bps, 1000);
+        set_transfer_tax(&provider, data_token, 500, true);
+        let cached = plugin.transfer_tax(wallet, now + ttl - chrono::Duration::seconds(1)).await;
+        assert_eq!(cached.ghost_core_bps, 1000);
+        assert_eq!(plugin.transfer_tax(wallet, now + ttl).await, TransferTax::default());
+    }
+
+    #[tokio::test]
+    async fn unreadable_transfer_tax_is_none() {
+        let plugin = test_plugin();
+        let tax = plugin.transfer_tax(Address::repeat_byte(0xaa), chrono::Utc::now()).await;
+        assert_eq!(tax, TransferTax::default());
+    }
+
+    /// A plugin whose GhostCore can be reset `seconds_left` from now, in
+    /// reset epoch `epoch`.
+    fn plugin_with_reset(seconds_left: i64, epoch: u64) -> GhostnetPlugin<MockProvider> {
+        let config = GhostnetConfig::testnet();
+        let provider = Arc::new(MockProvider::new());
+        let deadline = chrono::Utc::now().timestamp() + seconds_left;
+        let timer = ResetTimer {
+            deadline: u64::try_from(deadline).unwrap(),
+            epoch,
+            penalty_bps: 0,
+        };
+        set_reset_timer(&provider, config.ghost_core, timer);
+        GhostnetPlugin::new(config, provider)
+    }
+
+    /// The GhostCore actions a wallet takes over a number of decisions.
+    async fn ghost_core_actions(
+        plugin: &GhostnetPlugin<MockProvider>,
+        wallet: &WalletState,
+    ) -> Vec<String> {
+        let mut actions = Vec::new();
+        for seed in 0..20 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let mut context =
+                PluginContext::new(chrono::Utc::now(), &mut rng, &serde_json::Value::Null);
+            let action = plugin
+                .decide_action(wallet, &BehaviorProfile::degen(), &mut context)
+                .await
+                .unwrap();
+            actions.extend(
+                action
+                    .map(|action| action.id.0)
+                    .filter(|id| {
+                        [ACTION_JACK_IN, ACTION_ADD_STAKE, ACTION_EXTRACT, ACTION_CLAIM_REWARDS]
+                            .contains(&id.as_str())
+                            || id == ACTION_APPLY_BOOST
+                    }),
+            );
+        }
+        actions
+    }
+
+    #[tokio::test]
+    async fn reset_timer_is_cached_for_its_ttl() {
+        let plugin = plugin_with_reset(3600, 1);
+        let ttl = plugin.config.behavior.reset_timer_ttl();
+        let now = chrono::Utc::now();
+
+        assert_eq!(plugin.reset_timer(now).await.map(|timer| timer.epoch), Some(1));
+        set_reset_timer(
+            &plugin.provider,
+            plugin.config.ghost_core,
+            ResetTimer {
+                epoch: 2,
+                ..ResetTimer::default()
+            },
+        );
+        let cached = plugin.reset_timer(now + ttl - chrono::Duration::seconds(1)).await;
+        assert_eq!(cached.map(|timer| timer.epoch), Some(1));
+        assert_eq!(plugin.reset_timer(now + ttl).await.map(|timer| timer.epoch), Some(2));
+    }
+
+    #[tokio::test]
+    async fn no_stakes_close_to_a_reset() {
+        let danger = i64::try_from(BehaviorSettings::default().reset_danger_secs).unwrap();
+        let (wallet, _) = arcade_setup(0);
+
+        // Far enough from the reset, wallets jack in
+        for seconds_left in [danger * 10, danger + 60] {
+            let plugin = plugin_with_reset(seconds_left, 1);
+            let actions = ghost_core_actions(&plugin, &wallet).await;
+            assert!(
+                actions.iter().any(|id| id == ACTION_JACK_IN),
+                "{seconds_left}s out: {actions:?}"
+            );
+        }
+
+        // Close to it they wait, as they do once it is due
+        for seconds_left in [danger - 60, 1, -60] {
+            let plugin = plugin_with_reset(seconds_left, 1);
+            let actions = ghost_core_actions(&plugin, &wallet).await;
+            assert!(actions.is_empty(), "{seconds_left}s out: {actions:?}");
+        }
+    }
+
+    #[tokio::test]
+    async fn positions_read_before_a_reset_are_not_acted_on() {
+        let plugin = plugin_with_reset(60, 2);
+        let (mut wallet, _) = arcade_setup(0);
+        let position = Position {
+            amount: U256::from(1_000_000_000_000_000_000_u128),
+            level: Level::Darknet,
+            entry_timestamp: 0,
+            last_add_timestamp: 0,
+            alive: true,
+            ghost_streak: 5,
+            pending_rewards: U256::ZERO,
+            effective_death_rate_bps: 0,
+            in_lock_period: false,
+            active_boosts: Vec::new(),
+        };
+        let mut state = GhostnetState {
+            position: Some(position),
+            reset_epoch: Some(1),
+            ..GhostnetState::default()
+        };
+        wallet.set_plugin_state(PLUGIN_ID, &state).unwrap();
+        plugin.update(wallet.address, |tracked| {
+            tracked.boosts.push(boost_offer(1).boost());
+        });
+
+        // Read in the epoch before, the position is neither extracted ahead of
+        // the next reset nor replaced, and its boosts are forgotten
+        let actions = ghost_core_actions(&plugin, &wallet).await;
+        assert!(actions.is_empty(), "acted on a stale position: {actions:?}");
+        assert!(plugin.tracked(wallet.address).boosts.is_empty());
+
+        // Reading the state again stamps the current epoch
+        let read: GhostnetState =
+            serde_json::from_value(plugin.read_state(wallet.address).await.unwrap()).unwrap();
+        assert_eq!(read.reset_epoch, Some(2));
+
+        // Once read in the current epoch, the position is extracted
+        state.reset_epoch = Some(2);
+        wallet.set_plugin_state(PLUGIN_ID, &state).unwrap();
+        let actions = ghost_core_actions(&plugin, &wallet).await;
+        assert!(!actions.is_empty());
+        assert!(actions.iter().all(|id| id == ACTION_EXTRACT), "{actions:?}");
+    }
+
+    /// A wallet whose SUBNET position is worth extracting.
+    fn extracting_wallet() -> WalletState {
+        let (mut wallet, _) = arcade_setup(0);
+        let position = Position {
+            amount: U256::from(1_000_000_000_000_000_000_000_u128),
+            level: Level::Subnet,
+            entry_timestamp: 1_700_000_000,
+            last_add_timestamp: 0,
+            alive: true,
+            ghost_streak: 5,
+            pending_rewards: U256::ZERO,
+            effective_death_rate_bps: 2500,
+            in_lock_period: false,
+            active_boosts: Vec::new(),
+        };
+        let state = GhostnetState {
+            position: Some(position),
+            ..GhostnetState::default()
+        };
+        wallet.set_plugin_state(PLUGIN_ID, &state).unwrap();
+        wallet
+    }
+
+    /// A plugin taking profits on every extraction worth it.
+    fn taking_profits<P: ChainProvider>(provider: Arc<P>) -> GhostnetPlugin<P> {
+        let mut config = GhostnetConfig::testnet();
+        config.behavior.base_extract_probability = 1.0;
+        config.behavior.extract_strategy = ExtractStrategy::TakeProfits { keep_bps: 5000 };
+        GhostnetPlugin::new(config, provider)
+    }
+
+    async fn decide_extract<P: ChainProvider>(
+        plugin: &GhostnetPlugin<P>,
+        wallet: &WalletState,
+    ) -> Action {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut context =
+            PluginContext::new(chrono::Utc::now(), &mut rng, &serde_json::Value::Null);
+        let action = plugin
+            .decide_action(wallet, &BehaviorProfile::degen(), &mut context)
+            .await
+            .unwrap()
+            .expect("should extract");
+        assert_eq!(action.id.as_str(), ACTION_EXTRACT);
+        action
+    }
+
+    #[test]
+    fn recognizes_reverts_without_reason() {
+        assert!(reverted_without_reason("execution reverted"));
+        assert!(reverted_without_reason("RPC error (3): execution reverted: "));
+        assert!(reverted_without_reason(
+            "server returned an error response: error code 3: execution reverted, data: \"0x\""
+        ));
+        assert!(!reverted_without_reason("RPC error (3): execution reverted: PositionLocked"));
+        assert!(!reverted_without_reason("connection failed: refused"));
+    }
+
+    #[tokio::test]
+    async fn extracts_in_part_where_supported() {
+        use evm_provider::mock_chain::{MockChainProvider, RecordedCall};
+
+        let partial_calls = |chain: &MockChainProvider| {
+            let selector = ghost_core::extract_1Call::SELECTOR;
+            chain
+                .calls()
+                .into_iter()
+                .filter(|call| {
+                    matches!(call, RecordedCall::Call(tx)
+                        if tx.data.as_ref().is_some_and(|data| data.starts_with(&selector)))
+                })
+                .count()
+        };
+        let wallet = extracting_wallet();
+
+        // GhostCore takes the amount: half the stake, less for degen patience
+        let chain = Arc::new(MockChainProvider::new());
+        let plugin = taking_profits(Arc::clone(&chain));
+        let action = decide_extract(&plugin, &wallet).await;
+        let amount = GhostnetPlugin::<MockChainProvider>::parse_extract_amount(&action.data);
+        assert_eq!(amount.unwrap(), Some(U256::from(425_000_000_000_000_000_000_u128)));
+        let (_, data, _) = plugin.build_tx(&action, &wallet).unwrap();
+        assert!(data.starts_with(&ghost_core::extract_1Call::SELECTOR));
+        decide_extract(&plugin, &wallet).await;
+        assert_eq!(partial_calls(&chain), 1, "checked once");
+
+        // A revert for a reason says nothing about support: extract in full,
+        // and check again next time
+        let chain = Arc::new(MockChainProvider::new());
+        let core_address = GhostnetConfig::testnet().ghost_core;
+        chain.revert_call(core_address, ghost_core::extract_1Call::SELECTOR, "PositionLocked");
+        let plugin = taking_profits(Arc::clone(&chain));
+        let action = decide_extract(&plugin, &wallet).await;
+        assert!(action.data.get("amount").is_none(), "{action:?}");
+        decide_extract(&plugin, &wallet).await;
+        assert_eq!(partial_calls(&chain), 2);
+
+        // GhostCore without the function: in full for good, after one check
+        let chain = Arc::new(MockChainProvider::new());
+        chain.revert_call(core_address, ghost_core::extract_1Call::SELECTOR, "");
+        let plugin = taking_profits(Arc::clone(&chain));
+        for _ in 0..3 {
+            let action = decide_extract(&plugin, &wallet).await;
+            assert!(action.data.get("amount").is_none(), "{action:?}");
+        }
+        assert_eq!(partial_calls(&chain), 1);
+    }
+
+    #[test]
+    fn partial_extractions_are_tracked_per_position() {
+        let plugin = test_plugin();
+        let mut wallet = extracting_wallet();
+        let amount = U256::from(300);
+        let action = Action::with_data(
+            ACTION_EXTRACT,
+            "Extract",
+            serde_json::json!({ "amount": amount.to_string() }),
+        );
+        let extracted = |wallet: &WalletState| {
+            let state = GhostnetPlugin::<MockProvider>::parse_state(wallet).unwrap();
+            GhostnetPlugin::<MockProvider>::with_tracked(state, plugin.tracked(wallet.address))
+                .extracted
+        };
+
+        // Failed extractions count for nothing
+        plugin.track_extract(&action, &wallet, &ActionResult::failure("reverted"));
+        assert_eq!(extracted(&wallet), U256::ZERO);
+
+        let mined = ActionResult::success(B256::repeat_byte(1));
+        plugin.track_extract(&action, &wallet, &mined);
+        plugin.track_extract(&action, &wallet, &mined);
+        assert_eq!(extracted(&wallet), U256::from(600));
+
+        // Full extractions carry no amount
+        plugin.track_extract(&Action::new(ACTION_EXTRACT, "Extract"), &wallet, &mined);
+        assert_eq!(extracted(&wallet), U256::from(600));
+
+        // A new position starts over
+        let mut state = GhostnetPlugin::<MockProvider>::parse_state(&wallet).unwrap();
+        state.position.as_mut().unwrap().entry_timestamp += 1;
+        wallet.set_plugin_state(PLUGIN_ID, &state).unwrap();
+        assert_eq!(extracted(&wallet), U256::ZERO);
+        plugin.track_extract(&action, &wallet, &mined);
+        assert_eq!(extracted(&wallet), amount);
+    }
+
+    #[tokio::test]
+    async fn invalid_state_is_an_error() {
+        let plugin = test_plugin();
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut context =
+            PluginContext::new(chrono::Utc::now(), &mut rng, &serde_json::Value::Null);
+
+        let mut wallet = WalletState::new("test".into(), Address::ZERO);
+        wallet.set_token_balance(plugin.config.data_token, U256::from(1), 1);
+        wallet
+            .set_raw_plugin_state(PLUGIN_ID, serde_json::json!({ "position": 5 }), 1)
+            .unwrap();
+        let result = plugin
+            .decide_action(&wallet, &BehaviorProfile::degen(), &mut context)
+            .await;
+        assert!(
+            matches!(
+                result,
+                Err(fleet_core::FleetError::PluginState { ref plugin, .. }) if plugin == PLUGIN_ID
+            ),
+            "unexpected result: {result:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn warming_wallets_follow_their_plan() {
+        let plugin = test_plugin();
+        let now = chrono::Utc::now();
+        let settings = WarmupSettings {
+            enabled: true,
+            ..WarmupSettings::default()
+        };
+        let (mut wallet, _) = arcade_setup(0);
+        assert!(wallet.start_warmup(&settings, 1, now));
+        let plan = wallet.warmup.clone().unwrap();
+        let first = plan.steps[0];
+
+        for seed in 0..10 {
+            // Between steps the wallet waits, where it would otherwise jack in
+            let mut rng = StdRng::seed_from_u64(seed);
+            let mut context = PluginContext::new(now, &mut rng, &serde_json::Value::Null);
+            let action = plugin
+                .decide_action(&wallet, &BehaviorProfile::degen(), &mut context)
+                .await
+                .unwrap();
+            assert!(action.is_none(), "acted before the first step: {action:?}");
+        }
+
+        // A stake step enters at the lowest level
+        let data_token = GhostnetConfig::testnet().data_token;
+        let balance = wallet.token_balance(data_token);
+        wallet.set_tracked_balance(data_token, TrackedBalance::at(balance, 2, first.due_at));
+        wallet.warmup.as_mut().unwrap().steps[0].kind = WarmupStepKind::Stake;
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut context = PluginContext::new(first.due_at, &mut rng, &serde_json::Value::Null);
+        let action = plugin
+            .decide_action(&wallet, &BehaviorProfile::degen(), &mut context)
+            .await
+            .unwrap()
+            .expect("should stake");
+        assert_eq!(action.id.as_str(), ACTION_JACK_IN);
+        assert_eq!(GhostnetPlugin::<MockProvider>::parse_level(&action.data).unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn retiring_wallets_only_exit() {
+        let plugin = test_plugin();
+        let now = chrono::Utc::now();
+        let (mut wallet, _) = arcade_setup(0);
+        wallet.start_retiring(None, now);
+
+        // Nothing open: the wallet holds plenty of DATA but enters nothing
+        assert!(!plugin.has_open_positions(&wallet));
+        assert!(!plugin.can_possibly_act(&wallet, now));
+        for seed in 0..10 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let mut context = PluginContext::new(now, &mut rng, &serde_json::Value::Null);
+            let action = plugin
+                .decide_action(&wallet, &BehaviorProfile::degen(), &mut context)
+                .await
+                .unwrap();
+            assert!(action.is_none(), "retiring wallet acted: {action:?}");
+        }
+
+        // An alive position is extracted in full, however it is doing
+        let mut wallet = extracting_wallet();
+        wallet.start_retiring(None, now);
+        assert!(plugin.has_open_positions(&wallet));
+        assert!(plugin.can_possibly_act(&wallet, now));
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut context = PluginContext::new(now, &mut rng, &serde_json::Value::Null);
+        let action = plugin
+            .decide_action(&wallet, &BehaviorProfile::whale(), &mut context)
+            .await
+            .unwrap()
+            .expect("should extract");
+        assert_eq!(action.id.as_str(), ACTION_EXTRACT);
+        assert!(plugin.is_exit_action(&action.id));
+        assert!(!plugin.is_exit_action(&ActionId::new(ACTION_JACK_IN)));
+    }
+
+    /// A wallet with 1000 DATA that cannot jack in, so only arcade games
+    /// are left to play.
+    fn arcade_setup(seed: u64) -> (WalletState, StdRng) {
+        let mut wallet = WalletState::new("test".into(), Address::ZERO);
+        let balance = U256::from(1_000_000_000_000_000_000_000_u128);
+        wallet.set_token_balance(GhostnetConfig::testnet().data_token, balance, 1);
+        (wallet, StdRng::seed_from_u64(seed))
+    }
+
+    fn arcade_context(rng: &mut StdRng) -> PluginContext<'_> {
+        let now = chrono::Utc::now();
+        let cooldowns = fleet_core::plugins::ActionCooldowns::new()
+            .with_cooldown(ACTION_JACK_IN, chrono::Duration::hours(1))
+            .with_last_executed(ACTION_JACK_IN, now);
+        PluginContext::new(now, rng, &serde_json::Value::Null).with_cooldowns(cooldowns)
+    }
+
+    #[tokio::test]
+    async fn plays_listed_arcade_games() {
+        use ghostnet_abi::arcade_core;
+
+        let mut config = GhostnetConfig::testnet();
+        config.arcade_games.deny = vec![2];
+        let provider = Arc::new(MockProvider::new());
+        let listing = |id: u64| arcade_core::GameListing {
+            gameId: U256::from(id),
+            game: Address::repeat_byte(0x20),
+            minEntry: U256::from(1_000_000_000_000_000_000_u128),
+            maxEntry: U256::from(5_000_000_000_000_000_000_u128),
+            rakeBps: 300,
+            paused: false,
+        };
+        let games = arcade_core::getGamesCall::abi_encode_returns(&vec![listing(1), listing(2)]);
+        provider.register_call_response(
+            config.arcade_core,
+            arcade_core::getGamesCall::SELECTOR,
+            games.into(),
+        );
+        let plugin = GhostnetPlugin::new(config, provider);
+
+        let mut played = 0;
+        for seed in 0..20 {
+            let (wallet, mut rng) = arcade_setup(seed);
+            let mut context = arcade_context(&mut rng);
+            let Some(action) = plugin
+                .decide_action(&wallet, &BehaviorProfile::degen(), &mut context)
+                .await
+                .unwrap()
+            else {
+                continue;
+            };
+            assert_eq!(action.id.as_str(), ACTION_ARCADE_PLAY);
+            assert_eq!(action.data["game_id"], 1, "game 2 is denied");
+            let amount = GhostnetPlugin::<MockProvider>::parse_amount(&action.data, "amount");
+            assert!(amount.unwrap() <= U256::from(5_000_000_000_000_000_000_u128));
+
+            let calldata = plugin.build_transaction(&action, &wallet, 0).await.unwrap();
+            let call = arcade_core::playCall::abi_decode(&calldata).unwrap();
+            assert_eq!(call.gameId, U256::from(1));
+            played += 1;
+        }
+        assert!(played > 0, "no wallet played");
+    }
+
+    #[tokio::test]
+    async fn arcade_not_deployed_offers_nothing() {
+        // Calls to an address without code return no data
+        let plugin = test_plugin();
+
+        for seed in 0..20 {
+            let (wallet, mut rng) = arcade_setup(seed);
+            let mut context = arcade_context(&mut rng);
+            let action = plugin
+                .decide_action(&wallet, &BehaviorProfile::degen(), &mut context)
+                .await
+                .unwrap();
+            assert!(action.is_none(), "unexpected action: {action:?}");
+        }
+    }
+
+    /// Make round 1 the only DeadPool round, open with the given pools (in
+    /// DATA).
+    fn set_dead_pool_round(provider: &MockProvider, dead_pool: Address, over: u128, under: u128) {
+        use ghostnet_abi::dead_pool;
+
+        let data = |tokens: u128| U256::from(tokens * 1_000_000_000_000_000_000);
+        let count = dead_pool::roundCountCall::abi_encode_returns(&U256::from(1));
+        provider.register_call_response(
+            dead_pool,
+            dead_pool::roundCountCall::SELECTOR,
+            count.into(),
+        );
+        let round = dead_pool::getRoundCall::abi_encode_returns(&dead_pool::Round {
+            roundType: 0,
+            targetLevel: 1,
+            line: U256::from(50),
+            overPool: data(over),
+            underPool: data(under),
+            deadline: u64::from(u32::MAX),
+            resolveTime: 0,
+            resolved: false,
+            outcome: false,
+        });
+        provider.register_call_response(dead_pool, dead_pool::getRoundCall::SELECTOR, round.into());
+    }
+
+    #[tokio::test]
+    async fn dead_pool_bets_are_dropped_when_the_odds_move() {
+        use ghostnet_abi::dead_pool;
+
+        let dead_pool = Address::repeat_byte(0x05);
+        let mut config = GhostnetConfig::testnet();
+        config.dead_pool = Some(dead_pool);
+        let provider = Arc::new(MockProvider::new());
+        set_dead_pool_round(&provider, dead_pool, 300, 100);
+        let plugin = GhostnetPlugin::new(config, provider);
+
+        let mut decided = None;
+        for seed in 0..20 {
+            let (wallet, mut rng) = arcade_setup(seed);
+            let mut context = arcade_context(&mut rng);
+            let action = plugin
+                .decide_action(&wallet, &BehaviorProfile::degen(), &mut context)
+                .await
+                .unwrap();
+            if let Some(action) = action.filter(|action| action.id.as_str() == ACTION_DEAD_POOL_BET)
+            {
+                decided = Some((wallet, action));
+                break;
+            }
+        }
+        let (wallet, action) = decided.expect("no wallet bet on the round");
+        assert_eq!(action.data["round_id"], 1);
+        assert_eq!(action.data["is_over"], false, "the crowd is on OVER");
+        let calldata = plugin.build_transaction(&action, &wallet, 0).await.unwrap();
+        let call = dead_pool::placeBetCall::abi_decode(&calldata).unwrap();
+        assert_eq!(call.roundId, U256::from(1));
+        assert!(!call.isOver);
+
+        // The crowd follows onto UNDER before the bet is sent
+        set_dead_pool_round(&plugin.provider, dead_pool, 300, 300);
+        let result = plugin.execute_action(&action, &wallet, 0).await.unwrap();
+        assert_eq!(result.status, ActionStatus::Skipped);
+        let reason = result.detail["reason"].as_str().unwrap();
+        assert!(reason.contains("payout fell"), "{reason}");
+
+        // At the odds decided on, it goes ahead
+        set_dead_pool_round(&plugin.provider, dead_pool, 300, 100);
+        let result = plugin.execute_action(&action, &wallet, 0).await.unwrap();
+        assert_ne!(result.status, ActionStatus::Skipped);
+        assert_eq!(result.detail["gas_limit"], 120_000);
+    }
+
+    fn set_round(provider: &MockProvider, hash_crash: Address, state: u8, crash: u64) {
+        let round = hash_crash::getRoundCall::abi_encode_returns(&hash_crash::getRoundReturn {
+            state,
+            bettingEndTime: 0,
+            prizePool: U256::ZERO,
+            crashMultiplier: U256::from(crash),
+            totalPaidOut: U256::ZERO,
+            playerCount: U256::from(1),
+        });
+        let selector = hash_crash::getRoundCall::SELECTOR;
+        provider.register_call_response(hash_crash, selector, round.into());
+    }
+
+    #[tokio::test]
+    async fn reconciles_bets_both_ways() {
+        let plugin = test_plugin();
+        let hash_crash = plugin.contracts.hash_crash;
+        let player = Address::repeat_byte(0xAA);
+        let bet = hash_crash::getPlayerBetCall::abi_encode_returns(&hash_crash::getPlayerBetReturn {
+            amount: U256::from(95),
+            grossAmount: U256::from(100),
+            targetMultiplier: U256::from(200),
+            settled: true,
+        });
+        plugin.provider.register_call_response(
+            hash_crash,
+            hash_crash::getPlayerBetCall::SELECTOR,
+            bet.into(),
+        );
+        plugin.record_bet(player, 1, U256::from(100), 200, 0);
+        plugin.record_bet(player, 2, U256::from(50), 300, 0);
+
+        // Locked, crash point not revealed yet
+        set_round(&plugin.provider, hash_crash, 2, 0);
+        assert_eq!(plugin.reconcile_bets().await, 0);
+
+        // Crashed at 2.50x: the 2.00x target wins on the bet net of rake,
+        // the 3.00x one loses
+        set_round(&plugin.provider, hash_crash, 3, 250);
+        assert_eq!(plugin.reconcile_bets().await, 2);
+        let ledger = plugin.pnl(player);
+        assert_eq!(ledger.bets[0].outcome, BetOutcome::Won);
+        assert_eq!(ledger.bets[0].payout, U256::from(190));
+        assert_eq!(ledger.bets[1].outcome, BetOutcome::Lost);
+        assert_eq!(ledger.total_wagered, U256::from(150));
+        assert_eq!(ledger.total_won, U256::from(190));
+        assert_eq!(ledger.net.to_string(), "40");
+        assert_eq!(ledger.consecutive_losses(), 1);
+
+        // The winnings are paid out once, under the round
+        let wallet = WalletState::new("test".into(), player);
+        assert_eq!(
+            plugin.settled_flows(&wallet),
+            [(
+                "hashcrash:1".to_string(),
+                DataFlow::Received {
+                    amount: U256::from(190)
+                }
+            )]
+        );
+
+        // Nothing left to resolve, and the ledger shows up in the plugin state
+        assert_eq!(plugin.reconcile_bets().await, 0);
+        let state: GhostnetState =
+            serde_json::from_value(plugin.read_state(player).await.unwrap()).unwrap();
+        assert_eq!(state.pnl, ledger);
+    }
+
+    #[tokio::test]
+    async fn unreadable_rounds_stay_pending() {
+        let plugin = test_plugin();
+        let player = Address::repeat_byte(0xAA);
+        plugin.record_bet(player, 1, U256::from(100), 200, 0);
+
+        // No getRound response registered: the mock returns empty data
+        assert!(plugin.reconcile_wallet(player).await.is_err());
+        assert_eq!(plugin.reconcile_bets().await, 0);
+        assert_eq!(plugin.pnl(player).pending_rounds(), vec![1]);
+
+        set_round(&plugin.provider, plugin.contracts.hash_crash, RoundView::CANCELLED, 0);
+        assert_eq!(plugin.reconcile_wallet(player).await.unwrap(), 1);
+        let ledger = plugin.pnl(player);
+        assert_eq!(ledger.bets[0].outcome, BetOutcome::Refunded);
+        assert_eq!(ledger.net, I256::ZERO);
+    }
+
+    #[tokio::test]
+    async fn bet_results_carry_pnl() {
+        let plugin = test_plugin();
+        let wallet = WalletState::new("test".into(), Address::repeat_byte(0xAA));
+        plugin.record_bet(wallet.address, 1, U256::from(100), 200, 0);
+
+        let bet = Action::with_data(
+            ACTION_HASHCRASH_BET,
+            "HashCrash Bet",
+            serde_json::json!({ "round_id": 2, "amount": "50", "target_multiplier": 200 }),
+        );
+        let result = plugin.execute_action(&bet, &wallet, 0).await.unwrap();
+
+        // The failed bet is not recorded, but the ledger is reported
+        let pnl = &result.detail["pnl"];
+        assert_eq!(pnl["total_wagered"], "100");
+        assert_eq!(pnl["net"], "-100");
+        assert_eq!(pnl["pending_bets"], 1);
+        assert_eq!(result.detail["round_id"], 2);
+    }
+
+    fn boost_offer(nonce: u8) -> BoostOffer {
+        BoostOffer {
+            boost_type: BoostType::DeathReduction,
+            value_bps: 1000,
+            expiry: u64::MAX,
+            nonce: B256::repeat_byte(nonce),
+            signature: Bytes::from(vec![0xAB; 65]),
+            cost: U256::from(10),
+        }
+    }
+
+    #[tokio::test]
+    async fn offered_boosts_are_tracked() {
+        let plugin = test_plugin();
+        let player = Address::repeat_byte(0xAA);
+        plugin.offer_boost(player, boost_offer(1));
+        plugin.offer_boost(player, boost_offer(1));
+        plugin.offer_boost(player, boost_offer(2));
+
+        let state: GhostnetState =
+            serde_json::from_value(plugin.read_state(player).await.unwrap()).unwrap();
+        assert_eq!(state.boost_offers.len(), 2, "same grant offered twice");
+        assert_eq!(state.boost_spent, U256::ZERO);
+
+        // Applied boosts count as active on the position
+        let mut tracked = plugin.tracked(player);
+        tracked.boosts.push(boost_offer(1).boost());
+        let position = Position {
+            amount: U256::from(1),
+            level: Level::Darknet,
+            entry_timestamp: 0,
+            last_add_timestamp: 0,
+            alive: true,
+            ghost_streak: 0,
+            pending_rewards: U256::ZERO,
+            effective_death_rate_bps: 0,
+            in_lock_period: false,
+            active_boosts: vec![boost_offer(1).boost()],
+        };
+        let state = GhostnetState {
+            position: Some(position),
+            ..GhostnetState::default()
+        };
+        let state = GhostnetPlugin::<MockProvider>::with_tracked(state, tracked);
+        let position = state.position.unwrap();
+        assert_eq!(position.active_boosts.len(), 1, "chain already shows it");
+        assert!(position.has_active_boost(BoostType::DeathReduction, 0));
+    }
+
+    #[test]
+    fn build_apply_boost_tx() {
+        let plugin = test_plugin();
+        let wallet = WalletState::new("test".into(), Address::ZERO);
+
+        let offer = boost_offer(1);
+        let action = Action::with_data(
+            ACTION_APPLY_BOOST,
+            "Apply Boost",
+            serde_json::json!({ "offer": offer }),
+        );
+        let (to, data, value) = plugin.build_tx(&action, &wallet).unwrap();
+        assert_eq!(to, plugin.contracts.ghost_core);
+        assert_eq!(data, plugin.contracts.encode_apply_boost(&offer));
+        assert_eq!(value, U256::ZERO);
+
+        let bad = Action::with_data(ACTION_APPLY_BOOST, "Apply Boost", serde_json::json!({}));
+        assert!(plugin.build_tx(&bad, &wallet).is_err());
+    }
+
+    #[tokio::test]
+    async fn reads_active_boosts() {
+        let config = GhostnetConfig::testnet();
+        let ghost_core = config.ghost_core;
+        let provider = Arc::new(MockProvider::new());
+        let boosts = vec![ghost_core::Boost {
+            boostType: 1,
+            valueBps: 1500,
+            expiry: 99,
+        }];
+        let data = ghost_core::getActiveBoostsCall::abi_encode_returns(&boosts);
+        let selector = ghost_core::getActiveBoostsCall::SELECTOR;
+        provider.register_call_response(ghost_core, selector, data.into());
+
+        let plugin = GhostnetPlugin::new(config, provider);
+        let active = plugin.read_active_boosts(Address::ZERO).await.unwrap();
+        assert_eq!(active.len(), 1);
+        assert_eq!(active[0].boost_type, BoostType::YieldMultiplier);
+        assert!(active[0].is_active(98) && !active[0].is_active(99));
+    }
+
+    #[test]
+    fn receipt_events_update_state() {
+        use ghostnet_abi::ghost_core::{JackedIn, StakeAdded};
+
+        let plugin = test_plugin();
+        let player = Address::repeat_byte(0xAA);
+        let wallet = WalletState::new("test".into(), player);
+        let receipt_with = |events: Vec<alloy::primitives::LogData>, address| {
+            let logs = events
+                .into_iter()
+                .map(|data| alloy::rpc::types::Log {
+                    inner: alloy::primitives::Log { address, data },
+                    ..Default::default()
+                })
+                .collect();
+            TransactionReceipt {
+                tx_hash: B256::repeat_byte(1),
+                block_hash: B256::ZERO,
+                block_number: 1,
+                tx_index: 0,
+                from: player,
+                to: Some(address),
+                contract_address: None,
+                gas_used: 21_000,
+                success: true,
+                logs,
+            }
+        };
+        let action = Action::new(ACTION_JACK_IN, "Jack In");
+
+        let jacked_in = JackedIn {
+            user: player,
+            amount: U256::from(100),
+            level: Level::Darknet.as_u8(),
+            newTotal: U256::from(100),
+        };
+        let added = StakeAdded {
+            user: player,
+            amount: U256::from(50),
+            newTotal: U256::from(150),
+        };
+        let ghost_core = plugin.contracts.ghost_core;
+        let receipt = receipt_with(
+            vec![jacked_in.encode_log_data(), added.encode_log_data()],
+            ghost_core,
+        );
+        let state = plugin
+            .state_from_receipt(&action, &wallet, &receipt)
+            .expect("events should apply");
+        let state: GhostnetState = serde_json::from_value(state).unwrap();
+        let position = state.position.clone().unwrap();
+        assert_eq!(position.amount, U256::from(150));
+        assert_eq!(position.level, Level::Darknet);
+        assert_eq!(position.ghost_streak, 0);
+
+        // The stakes show up in the wallet's activity
+        let mut wallet = wallet;
+        wallet.set_plugin_state(PLUGIN_ID, &state).unwrap();
+        let activity = plugin.activity(&wallet).unwrap();
+        assert_eq!(activity.staked, U256::from(150));
+        assert_eq!(activity.extracted, U256::ZERO);
+        assert_eq!(activity.positions, ["DARKNET: 150 wei"]);
+
+        // Nothing from GhostCore falls back to reading state
+        let elsewhere = receipt_with(vec![jacked_in.encode_log_data()], Address::repeat_byte(9));
+        assert!(plugin.state_from_receipt(&action, &wallet, &elsewhere).is_none());
+    }
+
+    #[test]
+    fn receipts_give_data_flows() {
+        use ghostnet_abi::ghost_core::{Extracted, JackedIn};
+
+        let plugin = test_plugin();
+        let player = Address::repeat_byte(0xAA);
+        let wallet = WalletState::new("test".into(), player);
+        let receipt = |logs: Vec<(Address, alloy::primitives::LogData)>| TransactionReceipt {
+            tx_hash: B256::repeat_byte(1),
+            block_hash: B256::ZERO,
+            block_number: 1,
+            tx_index: 0,
+            from: player,
+            to: Some(plugin.contracts.ghost_core),
+            contract_address: None,
+            gas_used: 21_000,
+            success: true,
+            logs: logs
+                .into_iter()
+                .map(|(address, data)| alloy::rpc::types::Log {
+                    inner: alloy::primitives::Log { address, data },
+                    ..Default::default()
+                })
+                .collect(),
+        };
+        let (ghost_core, token) = (plugin.contracts.ghost_core, plugin.contracts.data_token);
+        let other = Address::repeat_byte(0xBB);
+
+        // Jacking in opens a position; another player's entry is not ours
+        let jacked_in = |user| JackedIn {
+            user,
+            amount: U256::from(100),
+            level: Level::Darknet.as_u8(),
+            newTotal: U256::from(100),
+        };
+        let jack_in = receipt(vec![
+            (ghost_core, jacked_in(player).encode_log_data()),
+            (ghost_core, jacked_in(other).encode_log_data()),
+        ]);
+        let action = Action::new(ACTION_JACK_IN, "Jack In");
+        assert_eq!(
+            plugin.data_flows(&action, &wallet, &jack_in),
+            [DataFlow::Staked {
+                amount: U256::from(100),
+                opened: true
+            }]
+        );
+
+        // A partial extraction takes out the amount asked for
+        let extracted = Extracted {
+            user: player,
+            amount: U256::from(40),
+            rewards: U256::from(7),
+        };
+        let extract = Action::with_data(
+            ACTION_EXTRACT,
+            "Extract",
+            serde_json::json!({ "amount": "40" }),
+        );
+        let receipt_of_extract = receipt(vec![(ghost_core, extracted.encode_log_data())]);
+        assert_eq!(
+            plugin.data_flows(&extract, &wallet, &receipt_of_extract),
+            [DataFlow::Exited {
+                principal: Some(U256::from(40)),
+                proceeds: U256::from(47)
+            }]
+        );
+
+        // Bets are wagered as they go through
+        let bet = Action::with_data(
+            ACTION_HASHCRASH_BET,
+            "HashCrash Bet",
+            serde_json::json!({ "round_id": 2, "amount": "50", "target_multiplier": 200 }),
+        );
+        assert_eq!(
+            plugin.data_flows(&bet, &wallet, &receipt(vec![])),
+            [DataFlow::Wagered {
+                amount: U256::from(50)
+            }]
+        );
+
+        // Claimed rewards are the DATA transferred to the player
+        let transfer = |to| data_token::Transfer {
+            from: ghost_core,
+            to,
+            value: U256::from(12),
+        };
+        let claim = receipt(vec![
+            (token, transfer(player).encode_log_data()),
+            (token, transfer(other).encode_log_data()),
+            (Address::repeat_byte(9), transfer(player).encode_log_data()),
+        ]);
+        let action = Action::new(ACTION_CLAIM_REWARDS, "Claim Rewards");
+        assert_eq!(
+            plugin.data_flows(&action, &wallet, &claim),
+            [DataFlow::Received {
+                amount: U256::from(12)
+            }]
+        );
+
+        // Nothing moves in a reverted transaction
+        let reverted = TransactionReceipt {
+            success: false,
+            ..claim
+        };
+        assert!(plugin.data_flows(&action, &wallet, &reverted).is_empty());
+    }
 }
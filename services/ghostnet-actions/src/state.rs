@@ -1,10 +1,32 @@
 //! GHOSTNET-specific state types.
 //!
 //! This module defines the state structures stored in `WalletState.plugin_states["ghostnet"]`.
+//!
+//! # Schema
+//!
+//! [`GhostnetState`] is what `read_state` returns and what engines and
+//! dashboards read, so its JSON form is a versioned schema: field names are
+//! part of it, amounts are hex strings of wei, and the golden files in
+//! `tests/snapshots` pin its shape. Every field is optional and unknown
+//! fields are ignored, so adding a field does not need a new version;
+//! renaming, removing or changing the meaning of one does, along with a
+//! conversion in [`GhostnetState::from_value`].
+//!
+//! | Version | Change |
+//! |---------|--------|
+//! | 1 | Unversioned |
+//! | 2 | Adds `schema_version`; every field optional |
 
-use alloy::primitives::U256;
+use alloy::primitives::{Address, B256, Bytes, I256, U256};
+use fleet_core::wallet::PluginState;
 use serde::{Deserialize, Serialize};
 
+use crate::error::{GhostnetError, Result};
+use crate::math::{BPS_100_PERCENT, PositionValuation, RiskModel, net_extract_amount};
+
+/// Current schema version of [`GhostnetState`].
+pub const STATE_SCHEMA_VERSION: u32 = 2;
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // LEVEL ENUM
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -135,6 +157,10 @@ pub struct Position {
 
     /// Whether the position is in lock period.
     pub in_lock_period: bool,
+
+    /// Boosts applied to the position (possibly expired).
+    #[serde(default)]
+    pub active_boosts: Vec<ActiveBoost>,
 }
 
 impl Position {
@@ -149,6 +175,113 @@ impl Position {
     pub const fn can_add_stake(&self) -> bool {
         self.alive
     }
+
+    /// Check if a boost of this type is active at `now_unix`.
+    #[must_use]
+    pub fn has_active_boost(&self, boost_type: BoostType, now_unix: u64) -> bool {
+        self.active_boosts
+            .iter()
+            .any(|boost| boost.boost_type == boost_type && boost.is_active(now_unix))
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// BOOSTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Kinds of GhostCore position boosts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[repr(u8)]
+pub enum BoostType {
+    /// Lowers the position's effective death rate.
+    DeathReduction = 0,
+    /// Multiplies the position's reward earnings.
+    YieldMultiplier = 1,
+}
+
+impl BoostType {
+    /// Convert from the contract's `BoostType` value.
+    #[must_use]
+    pub const fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(Self::DeathReduction),
+            1 => Some(Self::YieldMultiplier),
+            _ => None,
+        }
+    }
+
+    /// Convert to the contract's `BoostType` value.
+    #[must_use]
+    pub const fn as_u8(self) -> u8 {
+        self as u8
+    }
+}
+
+/// A boost applied to a position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActiveBoost {
+    /// Kind of boost.
+    pub boost_type: BoostType,
+
+    /// Strength in basis points.
+    pub value_bps: u16,
+
+    /// When the boost expires (Unix timestamp).
+    pub expiry: u64,
+}
+
+impl ActiveBoost {
+    /// Check if the boost is still in effect at `now_unix`.
+    #[must_use]
+    pub const fn is_active(&self, now_unix: u64) -> bool {
+        now_unix < self.expiry
+    }
+}
+
+/// A boost the wallet may apply: a grant signed by the boost signer.
+///
+/// GhostCore does not sell boosts. They are granted off chain (e.g. for
+/// mini-game results) as EIP-712 signatures that `applyBoost` verifies, so
+/// what a boost costs is whatever the wallet gave up to get the grant.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BoostOffer {
+    /// Kind of boost.
+    pub boost_type: BoostType,
+
+    /// Strength in basis points.
+    pub value_bps: u16,
+
+    /// When the grant, and the boost once applied, expires (Unix timestamp).
+    pub expiry: u64,
+
+    /// Single-use grant nonce.
+    pub nonce: B256,
+
+    /// Boost signer's signature over the grant.
+    pub signature: Bytes,
+
+    /// DATA the grant costs (in wei), zero for earned boosts.
+    #[serde(default)]
+    pub cost: U256,
+}
+
+impl BoostOffer {
+    /// Check if the grant can still be applied at `now_unix`.
+    #[must_use]
+    pub const fn is_valid(&self, now_unix: u64) -> bool {
+        now_unix < self.expiry
+    }
+
+    /// The boost the grant applies.
+    #[must_use]
+    pub const fn boost(&self) -> ActiveBoost {
+        ActiveBoost {
+            boost_type: self.boost_type,
+            value_bps: self.value_bps,
+            expiry: self.expiry,
+        }
+    }
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -182,15 +315,420 @@ impl HashCrashRound {
     }
 }
 
+// ═══════════════════════════════════════════════════════════════════════════════
+// ARCADE GAMES
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// A game registered with ArcadeCore and open for play.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArcadeGame {
+    /// Game ID to play by.
+    pub game_id: u64,
+
+    /// Game contract address.
+    pub game: Address,
+
+    /// Smallest entry the game accepts (in wei).
+    pub min_entry: U256,
+
+    /// Largest entry the game accepts (in wei), `None` if unlimited.
+    pub max_entry: Option<U256>,
+
+    /// Rake taken from entries, the game's house edge (basis points).
+    pub rake_bps: u16,
+}
+
+impl ArcadeGame {
+    /// Clamp an entry amount to the game's limits.
+    #[must_use]
+    pub fn clamp_entry(&self, amount: U256) -> U256 {
+        let amount = amount.max(self.min_entry);
+        self.max_entry.map_or(amount, |max| amount.min(max))
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// DEADPOOL ROUNDS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Rake DeadPool burns from the pot of a resolved round (basis points).
+pub const DEAD_POOL_RAKE_BPS: u64 = 500;
+
+/// A DeadPool prediction round open for bets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeadPoolRound {
+    /// Round ID to bet by.
+    pub round_id: u64,
+
+    /// Round type (`IDeadPool.RoundType`: 0 death count, 1 whale death,
+    /// 2 streak record, 3 system reset).
+    pub round_type: u8,
+
+    /// Over/under line.
+    pub line: U256,
+
+    /// DATA bet on OVER (in wei).
+    pub over_pool: U256,
+
+    /// DATA bet on UNDER (in wei).
+    pub under_pool: U256,
+
+    /// When betting closes (Unix timestamp).
+    pub deadline: u64,
+
+    /// When the round was first seen open (Unix timestamp), taken as the
+    /// start of its betting window.
+    pub opened_at: u64,
+}
+
+impl DeadPoolRound {
+    /// Check if we can still place a bet.
+    #[must_use]
+    pub const fn can_bet(&self, now_unix: u64) -> bool {
+        now_unix < self.deadline
+    }
+
+    /// What a bet of `amount` on OVER (or UNDER) pays per DATA if its side
+    /// wins, net of the rake (basis points, 10000 = even money).
+    ///
+    /// The bet joins its side's pool, so it dilutes its own payout.
+    #[must_use]
+    pub fn payout_bps(&self, over: bool, amount: U256) -> u64 {
+        let side = if over {
+            self.over_pool
+        } else {
+            self.under_pool
+        };
+        let side = side.saturating_add(amount);
+        if side.is_zero() {
+            return 0;
+        }
+        let pot = self
+            .over_pool
+            .saturating_add(self.under_pool)
+            .saturating_add(amount);
+        let net = pot.saturating_mul(U256::from(BPS_100_PERCENT - DEAD_POOL_RAKE_BPS));
+        u64::try_from(net / side).unwrap_or(u64::MAX)
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// HASHCRASH PNL
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Most bets a [`PnlLedger`] keeps; the oldest settled ones go first.
+/// Totals still cover every bet.
+pub const MAX_BET_HISTORY: usize = 100;
+
+/// How a HashCrash bet ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BetOutcome {
+    /// The round's crash point is not known yet.
+    Pending,
+    /// The target was below the crash point.
+    Won,
+    /// The round crashed at or below the target.
+    Lost,
+    /// The round was cancelled or expired and the bet refunded.
+    Refunded,
+}
+
+/// A HashCrash bet placed by a wallet.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BetRecord {
+    /// Round the bet was placed in.
+    pub round_id: u64,
+
+    /// DATA wagered (in wei, before rake).
+    pub amount: U256,
+
+    /// Target multiplier in hundredths (200 = 2.00x).
+    pub target_multiplier: u16,
+
+    /// When the bet was placed (Unix timestamp).
+    pub placed_at: u64,
+
+    /// How the bet ended.
+    pub outcome: BetOutcome,
+
+    /// DATA paid out (in wei): the winnings of a won bet, the refund of a
+    /// refunded one.
+    pub payout: U256,
+}
+
+/// HashCrash profit and loss of a wallet.
+///
+/// Wagers count as soon as they are placed, so pending bets show up as a
+/// loss until their round resolves.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PnlLedger {
+    /// DATA wagered on bets that were not refunded (in wei).
+    pub total_wagered: U256,
+
+    /// DATA won (in wei).
+    pub total_won: U256,
+
+    /// DATA wagered on bets that lost (in wei).
+    #[serde(default)]
+    pub total_lost: U256,
+
+    /// `total_won - total_wagered` (in wei).
+    pub net: I256,
+
+    /// Recent bets, oldest first (at most [`MAX_BET_HISTORY`]).
+    pub bets: Vec<BetRecord>,
+}
+
+impl PnlLedger {
+    /// Record a newly placed bet.
+    pub fn record_bet(
+        &mut self,
+        round_id: u64,
+        amount: U256,
+        target_multiplier: u16,
+        placed_at: u64,
+    ) {
+        self.bets.push(BetRecord {
+            round_id,
+            amount,
+            target_multiplier,
+            placed_at,
+            outcome: BetOutcome::Pending,
+            payout: U256::ZERO,
+        });
+        self.total_wagered = self.total_wagered.saturating_add(amount);
+        self.update_net();
+        self.trim();
+    }
+
+    /// Rounds with bets still waiting for an outcome.
+    #[must_use]
+    pub fn pending_rounds(&self) -> Vec<u64> {
+        self.bets
+            .iter()
+            .filter(|bet| bet.outcome == BetOutcome::Pending)
+            .map(|bet| bet.round_id)
+            .collect()
+    }
+
+    /// Get the pending bet in a round, if any.
+    #[must_use]
+    pub fn pending_bet(&self, round_id: u64) -> Option<&BetRecord> {
+        self.bets
+            .iter()
+            .find(|bet| bet.round_id == round_id && bet.outcome == BetOutcome::Pending)
+    }
+
+    /// Settle the pending bet in a round.
+    ///
+    /// `payout` is what the wallet got back: the winnings for
+    /// [`BetOutcome::Won`], the refund for [`BetOutcome::Refunded`] and
+    /// ignored otherwise.
+    ///
+    /// # Returns
+    ///
+    /// `false` if the wallet has no pending bet in the round.
+    pub fn resolve(&mut self, round_id: u64, outcome: BetOutcome, payout: U256) -> bool {
+        if outcome == BetOutcome::Pending {
+            return false;
+        }
+        let Some(bet) = self
+            .bets
+            .iter_mut()
+            .find(|bet| bet.round_id == round_id && bet.outcome == BetOutcome::Pending)
+        else {
+            return false;
+        };
+
+        bet.outcome = outcome;
+        match outcome {
+            BetOutcome::Won => {
+                bet.payout = payout;
+                self.total_won = self.total_won.saturating_add(payout);
+            }
+            BetOutcome::Refunded => {
+                bet.payout = payout;
+                self.total_wagered = self.total_wagered.saturating_sub(bet.amount);
+            }
+            BetOutcome::Lost => {
+                self.total_lost = self.total_lost.saturating_add(bet.amount);
+            }
+            BetOutcome::Pending => {}
+        }
+        self.update_net();
+        self.trim();
+        true
+    }
+
+    /// Number of losses since the last win, ignoring pending and refunded
+    /// bets.
+    #[must_use]
+    pub fn consecutive_losses(&self) -> u32 {
+        let streak = self
+            .bets
+            .iter()
+            .rev()
+            .filter(|bet| matches!(bet.outcome, BetOutcome::Won | BetOutcome::Lost))
+            .take_while(|bet| bet.outcome == BetOutcome::Lost)
+            .count();
+        u32::try_from(streak).unwrap_or(u32::MAX)
+    }
+
+    /// Recompute [`net`](Self::net) from the totals.
+    fn update_net(&mut self) {
+        let signed = |amount: U256| I256::try_from(amount).unwrap_or(I256::MAX);
+        self.net = signed(self.total_won).saturating_sub(signed(self.total_wagered));
+    }
+
+    /// Drop the oldest settled bets beyond [`MAX_BET_HISTORY`].
+    fn trim(&mut self) {
+        let mut excess = self.bets.len().saturating_sub(MAX_BET_HISTORY);
+        if excess > 0 {
+            self.bets.retain(|bet| {
+                let drop = excess > 0 && bet.outcome != BetOutcome::Pending;
+                excess -= usize::from(drop);
+                !drop
+            });
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// EXIT TOLL
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// What GhostCore takes from a position on its way out.
+///
+/// Read from GhostCore's system reset state: a pending reset penalty is
+/// charged on the stake when the position is next settled, which extraction
+/// does first. The penalty is the same at every level.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExitToll {
+    /// Share of the stake withheld (basis points).
+    pub penalty_bps: u16,
+}
+
+impl ExitToll {
+    /// The toll on `stake` (in wei), rounded up.
+    #[must_use]
+    pub fn on(&self, stake: U256) -> U256 {
+        stake - net_extract_amount(stake, u64::from(self.penalty_bps))
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TRANSFER TAX
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// What DataToken takes from DATA moving between the wallet and the games.
+///
+/// Read from DataToken's tax rate and exclusions: a transfer is untaxed if
+/// either side is excluded, which the deployed game contracts usually are,
+/// so both are 0 unless the wallet moves DATA through a taxed contract.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransferTax {
+    /// Tax on DATA sent to or received from GhostCore (basis points).
+    pub ghost_core_bps: u16,
+
+    /// Tax on DATA sent to or received from ArcadeCore (basis points).
+    pub arcade_core_bps: u16,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// SYSTEM RESET
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// GhostCore's system reset timer.
+///
+/// Read from `getSystemReset`: once the deadline passes without a deposit
+/// pushing it back, anyone can trigger a reset, which takes a penalty from
+/// every stake and starts a new epoch.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResetTimer {
+    /// When the system can be reset (Unix timestamp), 0 if unknown.
+    pub deadline: u64,
+
+    /// Reset epoch, incremented by every reset.
+    pub epoch: u64,
+
+    /// Penalty pending from the last reset (basis points).
+    pub penalty_bps: u16,
+}
+
+impl ResetTimer {
+    /// Seconds left at `now` (Unix timestamp) before the system can be
+    /// reset, or `None` if the deadline is unknown.
+    #[must_use]
+    pub const fn seconds_left(&self, now: u64) -> Option<u64> {
+        if self.deadline == 0 {
+            None
+        } else {
+            Some(self.deadline.saturating_sub(now))
+        }
+    }
+
+    /// Check if the system can be reset within `within_secs` of `now`.
+    #[must_use]
+    pub const fn is_near(&self, now: u64, within_secs: u64) -> bool {
+        match self.seconds_left(now) {
+            Some(left) => left <= within_secs,
+            None => false,
+        }
+    }
+
+    /// What extracting costs while the last reset's penalty is pending.
+    #[must_use]
+    pub const fn exit_toll(&self) -> ExitToll {
+        ExitToll {
+            penalty_bps: self.penalty_bps,
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// CULLING RISK
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// How close a position is to being culled.
+///
+/// Read from GhostCore's `getCullingRisk`: when a full level takes a new
+/// entrant, a position from its bottom is culled and loses most of its stake.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CullingRisk {
+    /// Chance of being culled by the next entrant (basis points).
+    pub risk_bps: u16,
+
+    /// Whether the position is among those that may be culled.
+    pub eligible: bool,
+
+    /// How full the position's level is (basis points of its capacity).
+    pub capacity_bps: u16,
+}
+
+impl CullingRisk {
+    /// Check if the position may be culled with at least `min_risk_bps`.
+    #[must_use]
+    pub const fn is_imminent(&self, min_risk_bps: u16) -> bool {
+        self.eligible && self.risk_bps >= min_risk_bps
+    }
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // GHOSTNET STATE
 // ═══════════════════════════════════════════════════════════════════════════════
 
 /// Complete GHOSTNET state for a wallet.
 ///
-/// This is stored in `WalletState.plugin_states["ghostnet"]`.
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+/// This is stored in `WalletState.plugin_states["ghostnet"]`. Read it from
+/// JSON with [`from_value`](Self::from_value) and write it with
+/// [`to_value`](Self::to_value), see the [schema](self#schema).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct GhostnetState {
+    /// Schema version the state was written with.
+    pub schema_version: u32,
+
     /// Current GhostCore position (if any).
     pub position: Option<Position>,
 
@@ -206,11 +744,154 @@ pub struct GhostnetState {
     /// Current HashCrash round (if betting is open).
     pub hashcrash_round: Option<HashCrashRound>,
 
+    /// HashCrash bets and their profit and loss.
+    pub pnl: PnlLedger,
+
+    /// Boost grants the wallet may apply.
+    pub boost_offers: Vec<BoostOffer>,
+
+    /// DATA spent on boosts so far (in wei).
+    pub boost_spent: U256,
+
+    /// What extracting the position costs.
+    pub exit_toll: ExitToll,
+
+    /// What DataToken takes from DATA moving to and from the games.
+    pub transfer_tax: TransferTax,
+
+    /// How close the position is to being culled.
+    pub culling_risk: CullingRisk,
+
+    /// DATA taken out of the position by partial extractions (in wei).
+    pub extracted: U256,
+
+    /// GhostCore's system reset timer, if it could be read.
+    pub reset_timer: Option<ResetTimer>,
+
+    /// Reset epoch the position was read in, if known.
+    pub reset_epoch: Option<u64>,
+
     /// Timestamp when state was last refreshed.
     pub last_refresh: u64,
 }
 
+impl Default for GhostnetState {
+    fn default() -> Self {
+        Self {
+            schema_version: STATE_SCHEMA_VERSION,
+            position: None,
+            data_balance: U256::ZERO,
+            ghost_core_allowance: U256::ZERO,
+            arcade_core_allowance: U256::ZERO,
+            hashcrash_round: None,
+            pnl: PnlLedger::default(),
+            boost_offers: Vec::new(),
+            boost_spent: U256::ZERO,
+            exit_toll: ExitToll::default(),
+            transfer_tax: TransferTax::default(),
+            culling_risk: CullingRisk::default(),
+            extracted: U256::ZERO,
+            reset_timer: None,
+            reset_epoch: None,
+            last_refresh: 0,
+        }
+    }
+}
+
+impl PluginState for GhostnetState {
+    const SCHEMA_VERSION: u32 = STATE_SCHEMA_VERSION;
+
+    fn migrate(
+        _from_version: u32,
+        value: serde_json::Value,
+    ) -> std::result::Result<Self, serde_json::Error> {
+        Self::from_value(value).map_err(serde::de::Error::custom)
+    }
+}
+
 impl GhostnetState {
+    /// Read state from its JSON form, of this or an older schema version.
+    ///
+    /// The version is taken from the `schema_version` field, version 1 if
+    /// there is none, and older states are converted to the current schema.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GhostnetError::InvalidState`] naming the offending field if
+    /// the state is of a newer schema version, cannot be read, or holds
+    /// values GHOSTNET never reports.
+    pub fn from_value(value: serde_json::Value) -> Result<Self> {
+        let version = match value.get("schema_version") {
+            None => 1,
+            Some(version) => version
+                .as_u64()
+                .and_then(|version| u32::try_from(version).ok())
+                .ok_or_else(|| invalid_state("schema_version", "not a schema version"))?,
+        };
+        if version > STATE_SCHEMA_VERSION {
+            return Err(invalid_state(
+                "schema_version",
+                format!("version {version} is newer than {STATE_SCHEMA_VERSION}"),
+            ));
+        }
+
+        // Version 2 only added fields, so version 1 reads as is
+        let mut state: Self = serde_path_to_error::deserialize(value).map_err(|e| {
+            let field = e.path().to_string();
+            invalid_state(field, e.into_inner())
+        })?;
+        state.schema_version = STATE_SCHEMA_VERSION;
+        state.validate()?;
+        Ok(state)
+    }
+
+    /// Write the state in its JSON form, of the current schema version.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the state cannot be serialized.
+    pub fn to_value(&self) -> Result<serde_json::Value> {
+        let state = Self {
+            schema_version: STATE_SCHEMA_VERSION,
+            ..self.clone()
+        };
+        Ok(serde_json::to_value(state)?)
+    }
+
+    /// Check values that deserialize but GHOSTNET never reports.
+    fn validate(&self) -> Result<()> {
+        if let Some(position) = &self.position {
+            if !position.level.is_valid() {
+                return Err(invalid_state("position.level", "a position has a level"));
+            }
+            if position.effective_death_rate_bps > 10_000 {
+                return Err(invalid_state(
+                    "position.effective_death_rate_bps",
+                    "more than 10000 basis points",
+                ));
+            }
+        }
+        if self.exit_toll.penalty_bps > 10_000 {
+            return Err(invalid_state(
+                "exit_toll.penalty_bps",
+                "more than 10000 basis points",
+            ));
+        }
+        if self.transfer_tax.ghost_core_bps > 10_000 {
+            return Err(invalid_state(
+                "transfer_tax.ghost_core_bps",
+                "more than 10000 basis points",
+            ));
+        }
+        if self.transfer_tax.arcade_core_bps > 10_000 {
+            return Err(invalid_state(
+                "transfer_tax.arcade_core_bps",
+                "more than 10000 basis points",
+            ));
+        }
+        Ok(())
+    }
+
     /// Check if the wallet has an active position.
     #[must_use]
     pub fn has_active_position(&self) -> bool {
@@ -228,6 +909,50 @@ impl GhostnetState {
     pub fn active_position(&self) -> Option<&Position> {
         self.position.as_ref().filter(|p| p.alive)
     }
+
+    /// Check if GhostCore can be reset within `within_secs` of `now` (Unix
+    /// timestamp), which with no seconds to spare never is.
+    #[must_use]
+    pub fn is_reset_near(&self, now: u64, within_secs: u64) -> bool {
+        within_secs > 0
+            && self
+                .reset_timer
+                .is_some_and(|timer| timer.is_near(now, within_secs))
+    }
+
+    /// Check if the position was read before the last system reset.
+    #[must_use]
+    pub const fn predates_reset(&self) -> bool {
+        match (self.reset_epoch, self.reset_timer) {
+            (Some(epoch), Some(timer)) => epoch < timer.epoch,
+            _ => false,
+        }
+    }
+
+    /// Drop what was read about the position, e.g. after a system reset.
+    pub fn forget_position(&mut self) {
+        self.position = None;
+        self.culling_risk = CullingRisk::default();
+        self.extracted = U256::ZERO;
+        self.reset_epoch = None;
+    }
+
+    /// Value the active position, if any, with its death rate from `risk`,
+    /// net of the tax on DATA paid out by GhostCore.
+    #[must_use]
+    pub fn valuation(&self, risk: &(impl RiskModel + ?Sized)) -> Option<PositionValuation> {
+        let tax_bps = u64::from(self.transfer_tax.ghost_core_bps);
+        self.active_position()
+            .map(|position| PositionValuation::of_taxed(position, self.exit_toll, tax_bps, risk))
+    }
+}
+
+/// Error for state whose `field` cannot be read.
+fn invalid_state(field: impl Into<String>, message: impl std::fmt::Display) -> GhostnetError {
+    GhostnetError::InvalidState {
+        field: field.into(),
+        message: message.to_string(),
+    }
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -235,6 +960,7 @@ impl GhostnetState {
 // ═══════════════════════════════════════════════════════════════════════════════
 
 #[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
 mod tests {
     use super::*;
 
@@ -300,6 +1026,7 @@ mod tests {
             pending_rewards: U256::ZERO,
             effective_death_rate_bps: 2500,
             in_lock_period: false,
+            active_boosts: Vec::new(),
         };
 
         assert!(position.can_extract());
@@ -320,6 +1047,91 @@ mod tests {
         assert!(locked_position.can_add_stake());
     }
 
+    #[test]
+    fn boost_expiry() {
+        let boost = ActiveBoost {
+            boost_type: BoostType::DeathReduction,
+            value_bps: 500,
+            expiry: 100,
+        };
+        assert!(boost.is_active(99));
+        assert!(!boost.is_active(100));
+        assert_eq!(BoostType::from_u8(1), Some(BoostType::YieldMultiplier));
+        assert!(BoostType::from_u8(2).is_none());
+
+        let mut position = Position {
+            amount: U256::from(100),
+            level: Level::Subnet,
+            entry_timestamp: 0,
+            last_add_timestamp: 0,
+            alive: true,
+            ghost_streak: 0,
+            pending_rewards: U256::ZERO,
+            effective_death_rate_bps: 2500,
+            in_lock_period: false,
+            active_boosts: vec![boost],
+        };
+        assert!(position.has_active_boost(BoostType::DeathReduction, 50));
+        assert!(!position.has_active_boost(BoostType::YieldMultiplier, 50));
+        assert!(!position.has_active_boost(BoostType::DeathReduction, 150));
+        position.active_boosts.clear();
+        assert!(!position.has_active_boost(BoostType::DeathReduction, 50));
+    }
+
+    #[test]
+    fn pnl_ledger_math() {
+        let mut ledger = PnlLedger::default();
+        ledger.record_bet(1, U256::from(100), 200, 10);
+        ledger.record_bet(2, U256::from(50), 300, 20);
+        ledger.record_bet(3, U256::from(40), 150, 30);
+        assert_eq!(ledger.pending_rounds(), vec![1, 2, 3]);
+        assert_eq!(ledger.net, I256::try_from(-190).unwrap());
+
+        // Won at 2.00x on 95 net of rake, lost, refunded
+        assert!(ledger.resolve(1, BetOutcome::Won, U256::from(190)));
+        assert!(ledger.resolve(2, BetOutcome::Lost, U256::ZERO));
+        assert!(ledger.resolve(3, BetOutcome::Refunded, U256::from(40)));
+        assert!(!ledger.resolve(3, BetOutcome::Lost, U256::ZERO), "already settled");
+        assert!(!ledger.resolve(9, BetOutcome::Won, U256::from(1)), "no such bet");
+
+        assert_eq!(ledger.total_wagered, U256::from(150));
+        assert_eq!(ledger.total_won, U256::from(190));
+        assert_eq!(ledger.total_lost, U256::from(50));
+        assert_eq!(ledger.net, I256::try_from(40).unwrap());
+        assert!(ledger.pending_rounds().is_empty());
+        assert_eq!(ledger.bets[0].payout, U256::from(190));
+    }
+
+    #[test]
+    fn pnl_loss_streak_skips_refunds_and_pending() {
+        let mut ledger = PnlLedger::default();
+        for round in 1..=5 {
+            ledger.record_bet(round, U256::from(10), 200, round);
+        }
+        ledger.resolve(1, BetOutcome::Won, U256::from(20));
+        ledger.resolve(2, BetOutcome::Lost, U256::ZERO);
+        ledger.resolve(3, BetOutcome::Refunded, U256::from(10));
+        ledger.resolve(4, BetOutcome::Lost, U256::ZERO);
+        assert_eq!(ledger.consecutive_losses(), 2);
+
+        ledger.resolve(5, BetOutcome::Won, U256::from(20));
+        assert_eq!(ledger.consecutive_losses(), 0);
+    }
+
+    #[test]
+    fn pnl_history_keeps_pending_bets() {
+        let mut ledger = PnlLedger::default();
+        ledger.record_bet(0, U256::from(1), 200, 0);
+        for round in 1..=MAX_BET_HISTORY as u64 {
+            ledger.record_bet(round, U256::from(1), 200, round);
+            ledger.resolve(round, BetOutcome::Lost, U256::ZERO);
+        }
+
+        assert_eq!(ledger.bets.len(), MAX_BET_HISTORY);
+        assert_eq!(ledger.pending_rounds(), vec![0]);
+        assert_eq!(ledger.total_wagered, U256::from(MAX_BET_HISTORY + 1));
+    }
+
     #[test]
     fn ghostnet_state_position_helpers() {
         let mut state = GhostnetState::default();
@@ -337,6 +1149,7 @@ mod tests {
             pending_rewards: U256::ZERO,
             effective_death_rate_bps: 2500,
             in_lock_period: false,
+            active_boosts: Vec::new(),
         });
 
         assert!(state.has_active_position());
@@ -348,4 +1161,52 @@ mod tests {
         assert!(state.has_dead_position());
         assert!(state.active_position().is_none());
     }
+
+    #[test]
+    fn reset_timer_distance() {
+        let timer = ResetTimer {
+            deadline: 1000,
+            epoch: 2,
+            penalty_bps: 1000,
+        };
+        assert_eq!(timer.seconds_left(400), Some(600));
+        assert_eq!(timer.seconds_left(1200), Some(0));
+        assert!(!timer.is_near(400, 599));
+        assert!(timer.is_near(400, 600));
+        assert!(timer.is_near(1200, 0));
+        assert_eq!(timer.exit_toll(), ExitToll { penalty_bps: 1000 });
+
+        // An unknown deadline is never near
+        let unknown = ResetTimer::default();
+        assert_eq!(unknown.seconds_left(400), None);
+        assert!(!unknown.is_near(400, u64::MAX));
+    }
+
+    #[test]
+    fn positions_read_before_a_reset_are_stale() {
+        let timer = ResetTimer {
+            deadline: 1000,
+            epoch: 2,
+            penalty_bps: 0,
+        };
+        let mut state = GhostnetState {
+            reset_timer: Some(timer),
+            reset_epoch: Some(2),
+            ..GhostnetState::default()
+        };
+        assert!(!state.predates_reset());
+        assert!(state.is_reset_near(900, 100));
+        assert!(!state.is_reset_near(1000, 0));
+
+        state.reset_epoch = Some(1);
+        assert!(state.predates_reset());
+        state.forget_position();
+        assert!(!state.predates_reset());
+
+        // Without a timer or an epoch, nothing is known to be stale
+        state.reset_timer = None;
+        state.reset_epoch = Some(1);
+        assert!(!state.predates_reset());
+        assert!(!state.is_reset_near(900, 100));
+    }
 }
@@ -0,0 +1,430 @@
+//! Round trips of the GHOSTNET actions against a scripted chain.
+//!
+//! Each test decides an action from a wallet's state, executes it, sends the
+//! calldata the plugin builds and reads the state that results, all against
+//! a [`MockChainProvider`]. The calldata of fixed actions is compared with
+//! the snapshots in `tests/snapshots`, so a changed ABI or encoding shows up
+//! as a snapshot diff. Run with `UPDATE_SNAPSHOTS=1` to accept new calldata.
+
+#![allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::pedantic,
+    clippy::nursery
+)]
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use alloy::primitives::{Address, Bytes, U256};
+use alloy::sol_types::SolEvent;
+use chrono::Utc;
+use evm_provider::mock_chain::{Method, MockChainProvider, RecordedCall};
+use evm_provider::{ChainProvider, ProviderError};
+use fleet_core::plugins::{Action, ActionPlugin, ActionResult, ActionStatus, PluginContext};
+use fleet_core::profiles::BehaviorProfile;
+use fleet_core::wallet::WalletState;
+use ghostnet_abi::ghost_core::JackedIn;
+use ghostnet_actions::state::HashCrashRound;
+use ghostnet_actions::{GhostnetConfig, GhostnetPlugin, GhostnetState, Level, PLUGIN_ID, Position};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+const DATA: u128 = 1_000_000_000_000_000_000;
+const PLAYER: Address = Address::repeat_byte(0xAA);
+const NONCE: u64 = 7;
+
+/// Seeds tried to get a decider to pick an action.
+const SEEDS: u64 = 500;
+
+/// The actions every test covers, with fixed parameters.
+fn fixed_actions() -> Vec<(&'static str, Action)> {
+    vec![
+        (
+            "jack_in",
+            Action::with_data(
+                "ghostnet.jack_in",
+                "Jack In",
+                serde_json::json!({ "amount": (100 * DATA).to_string(), "level": 2 }),
+            ),
+        ),
+        (
+            "add_stake",
+            Action::with_data(
+                "ghostnet.add_stake",
+                "Add Stake",
+                serde_json::json!({ "amount": (25 * DATA).to_string() }),
+            ),
+        ),
+        ("extract", Action::new("ghostnet.extract", "Extract")),
+        (
+            "claim_rewards",
+            Action::new("ghostnet.claim_rewards", "Claim Rewards"),
+        ),
+        (
+            "hashcrash_bet",
+            Action::with_data(
+                "ghostnet.hashcrash_bet",
+                "HashCrash Bet",
+                serde_json::json!({ "amount": (5 * DATA).to_string(), "target_multiplier": 250 }),
+            ),
+        ),
+    ]
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// HARNESS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// A wallet and the plugin, against a scripted chain.
+struct Harness {
+    chain: Arc<MockChainProvider>,
+    plugin: GhostnetPlugin<MockChainProvider>,
+    wallet: WalletState,
+}
+
+/// What came of one action.
+struct RoundTrip {
+    /// Calldata the plugin built.
+    calldata: Bytes,
+    /// Result of the receipt of the sent transaction.
+    mined: ActionResult,
+    /// Wallet state after the action.
+    state: GhostnetState,
+    /// Whether the state came from the receipt rather than a read.
+    from_receipt: bool,
+}
+
+impl Harness {
+    fn new(state: &GhostnetState, data_balance: u128) -> Self {
+        let config = GhostnetConfig::testnet();
+        let chain = Arc::new(MockChainProvider::with_chain_id(config.chain_id));
+        let mut wallet = WalletState::new("wallet_1".into(), PLAYER);
+        wallet.set_nonce(NONCE);
+        wallet.set_token_balance(config.data_token, U256::from(data_balance), 1);
+        wallet.set_plugin_state(PLUGIN_ID, state).unwrap();
+        let plugin = GhostnetPlugin::new(config, Arc::clone(&chain));
+        Self {
+            chain,
+            plugin,
+            wallet,
+        }
+    }
+
+    /// Decide actions with successive seeds until the plugin picks `id`.
+    async fn decide(&self, id: &str) -> Action {
+        let profile = BehaviorProfile::degen();
+        for seed in 0..SEEDS {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let mut context = PluginContext::new(Utc::now(), &mut rng, &serde_json::Value::Null);
+            let action = self
+                .plugin
+                .decide_action(&self.wallet, &profile, &mut context)
+                .await
+                .unwrap();
+            if let Some(action) = action.filter(|action| action.id.as_str() == id) {
+                return action;
+            }
+        }
+        panic!("{id} never decided in {SEEDS} seeds");
+    }
+
+    /// Execute `action`, send its calldata as the orchestrator would and
+    /// take the state from the receipt, or read it.
+    async fn round_trip(&self, action: &Action) -> RoundTrip {
+        let executed = self
+            .plugin
+            .execute_action(action, &self.wallet, NONCE)
+            .await
+            .unwrap();
+        // The plugin only builds the transaction; signing is the fleet's
+        assert_eq!(executed.status, ActionStatus::Dropped);
+        assert_eq!(executed.detail["nonce"], NONCE);
+
+        let calldata = self
+            .plugin
+            .build_transaction(action, &self.wallet, NONCE)
+            .await
+            .unwrap();
+        assert_eq!(executed.detail["calldata_len"], calldata.len());
+
+        let tx_hash = self
+            .chain
+            .send_raw_transaction(calldata.clone())
+            .await
+            .unwrap();
+        let receipt = self
+            .chain
+            .wait_for_receipt(tx_hash, Duration::from_secs(1))
+            .await
+            .unwrap();
+        let mined = ActionResult::from_receipt(&receipt);
+
+        let (state, from_receipt) =
+            match self
+                .plugin
+                .state_from_receipt(action, &self.wallet, &receipt)
+            {
+                Some(state) => (state, true),
+                None => (self.plugin.read_state(PLAYER).await.unwrap(), false),
+            };
+        RoundTrip {
+            calldata,
+            mined,
+            state: serde_json::from_value(state).unwrap(),
+            from_receipt,
+        }
+    }
+}
+
+fn position(in_lock_period: bool, ghost_streak: u16, pending_rewards: u128) -> Position {
+    Position {
+        amount: U256::from(100 * DATA),
+        level: Level::Mainframe,
+        entry_timestamp: 0,
+        last_add_timestamp: 0,
+        alive: true,
+        ghost_streak,
+        pending_rewards: U256::from(pending_rewards),
+        effective_death_rate_bps: 1500,
+        in_lock_period,
+        active_boosts: Vec::new(),
+    }
+}
+
+fn with_position(position: Position) -> GhostnetState {
+    GhostnetState {
+        position: Some(position),
+        ..GhostnetState::default()
+    }
+}
+
+/// Compare calldata with its snapshot, one 32-byte word per line.
+fn assert_snapshot(name: &str, calldata: &Bytes) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/snapshots")
+        .join(format!("{name}.hex"));
+    let (selector, args) = calldata.split_at(4.min(calldata.len()));
+    let mut actual = format!("{}\n", alloy::hex::encode(selector));
+    for word in args.chunks(32) {
+        actual.push_str(&alloy::hex::encode(word));
+        actual.push('\n');
+    }
+
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        std::fs::write(&path, &actual).unwrap();
+        return;
+    }
+    let expected = std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("no snapshot at {}: {e}", path.display()));
+    assert_eq!(
+        actual, expected,
+        "calldata of {name} changed, rerun with UPDATE_SNAPSHOTS=1 to accept it"
+    );
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// CALLDATA SNAPSHOTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn calldata_matches_snapshots() {
+    let harness = Harness::new(&GhostnetState::default(), 1_000 * DATA);
+    for (name, action) in fixed_actions() {
+        let trip = harness.round_trip(&action).await;
+        assert_snapshot(name, &trip.calldata);
+        assert_eq!(trip.mined.status, ActionStatus::Succeeded, "{name}");
+    }
+
+    // Every transaction went out as built, and nothing else was sent
+    let sent = harness.chain.sent_transactions();
+    assert_eq!(sent.len(), fixed_actions().len());
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// DECIDE, EXECUTE, READ
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn decided_entry_updates_state_from_receipt() {
+    let harness = Harness::new(&GhostnetState::default(), 1_000 * DATA);
+    let action = harness.decide("ghostnet.jack_in").await;
+    let amount: U256 = action.data["amount"].as_str().unwrap().parse().unwrap();
+    let level = u8::try_from(action.data["level"].as_u64().unwrap()).unwrap();
+
+    // GhostCore emits the entry in the receipt of the sent calldata
+    let calldata = harness
+        .plugin
+        .build_transaction(&action, &harness.wallet, NONCE)
+        .await
+        .unwrap();
+    let event = JackedIn {
+        user: PLAYER,
+        amount,
+        level,
+        newTotal: amount,
+    };
+    let log = alloy::rpc::types::Log {
+        inner: alloy::primitives::Log {
+            address: harness.plugin.contracts().ghost_core,
+            data: event.encode_log_data(),
+        },
+        ..Default::default()
+    };
+    harness.chain.set_receipt_logs(&calldata, vec![log]);
+
+    let trip = harness.round_trip(&action).await;
+    assert_eq!(trip.calldata, calldata);
+    assert!(trip.from_receipt);
+    let position = trip.state.position.expect("entered");
+    assert_eq!((position.amount, position.level.as_u8()), (amount, level));
+    assert!(position.in_lock_period);
+}
+
+#[tokio::test]
+async fn decided_actions_round_trip() {
+    let round = HashCrashRound {
+        round_id: 1,
+        is_betting: true,
+        betting_ends_at: u64::MAX,
+        player_count: 0,
+        prize_pool: U256::ZERO,
+    };
+    let cases = [
+        (
+            "ghostnet.add_stake",
+            with_position(position(true, 0, 0)),
+            1_000 * DATA,
+        ),
+        (
+            "ghostnet.extract",
+            with_position(position(false, 10, 0)),
+            DATA,
+        ),
+        (
+            "ghostnet.claim_rewards",
+            with_position(position(true, 0, 100 * DATA)),
+            DATA,
+        ),
+        (
+            "ghostnet.hashcrash_bet",
+            GhostnetState {
+                hashcrash_round: Some(round),
+                ..with_position(position(true, 0, 0))
+            },
+            // Bets are at most 10% of the balance
+            50 * DATA,
+        ),
+    ];
+
+    for (id, state, balance) in cases {
+        let harness = Harness::new(&state, balance);
+        let action = harness.decide(id).await;
+        let fixed = fixed_actions()
+            .into_iter()
+            .find(|(_, fixed)| fixed.id == action.id)
+            .map(|(_, fixed)| fixed)
+            .unwrap();
+
+        let trip = harness.round_trip(&action).await;
+        assert_eq!(trip.mined.status, ActionStatus::Succeeded, "{id}");
+        assert!(!trip.from_receipt, "{id}: no events, state is read");
+
+        // Same function as the snapshot, with the decided arguments
+        let expected = harness
+            .plugin
+            .build_transaction(&fixed, &harness.wallet, NONCE)
+            .await
+            .unwrap();
+        assert_eq!(trip.calldata[..4], expected[..4], "{id}");
+        if let Some(amount) = action.data["amount"].as_str() {
+            let amount: U256 = amount.parse().unwrap();
+            assert_eq!(trip.calldata[4..36], amount.to_be_bytes::<32>(), "{id}");
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// INJECTED FAILURES
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn reverted_actions_fall_back_to_reading_state() {
+    for (name, action) in fixed_actions() {
+        let harness = Harness::new(&with_position(position(true, 0, 0)), 1_000 * DATA);
+
+        // Even with the events set up, a revert emits nothing
+        let calldata = harness
+            .plugin
+            .build_transaction(&action, &harness.wallet, NONCE)
+            .await
+            .unwrap();
+        let log = alloy::rpc::types::Log {
+            inner: alloy::primitives::Log {
+                address: harness.plugin.contracts().ghost_core,
+                data: JackedIn {
+                    user: PLAYER,
+                    amount: U256::from(DATA),
+                    level: 1,
+                    newTotal: U256::from(DATA),
+                }
+                .encode_log_data(),
+            },
+            ..Default::default()
+        };
+        harness.chain.set_receipt_logs(&calldata, vec![log]);
+        harness.chain.revert_next_transaction();
+
+        let trip = harness.round_trip(&action).await;
+        assert_eq!(trip.mined.status, ActionStatus::Reverted, "{name}");
+        assert!(!trip.mined.is_success(), "{name}");
+        assert!(!trip.from_receipt, "{name}");
+        assert!(
+            trip.state.position.is_none(),
+            "{name}: state read from chain"
+        );
+        assert_snapshot(name, &trip.calldata);
+    }
+}
+
+#[tokio::test]
+async fn failed_sends_are_recorded() {
+    let harness = Harness::new(&GhostnetState::default(), 1_000 * DATA);
+    let (_, action) = fixed_actions().remove(0);
+    let calldata = harness
+        .plugin
+        .build_transaction(&action, &harness.wallet, NONCE)
+        .await
+        .unwrap();
+
+    harness.chain.fail_next(
+        Method::SendRawTransaction,
+        ProviderError::NonceTooLow {
+            address: PLAYER,
+            expected: NONCE + 1,
+            actual: NONCE,
+        },
+    );
+    let err = harness
+        .chain
+        .send_raw_transaction(calldata.clone())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ProviderError::NonceTooLow { .. }));
+
+    // The retry is mined
+    let trip = harness.round_trip(&action).await;
+    assert_eq!(trip.mined.status, ActionStatus::Succeeded);
+    let sends: Vec<_> = harness
+        .chain
+        .calls_to(Method::SendRawTransaction)
+        .into_iter()
+        .map(|call| match call {
+            RecordedCall::SendRawTransaction(raw) => raw,
+            other => panic!("unexpected {other:?}"),
+        })
+        .collect();
+    assert_eq!(sends, [calldata.clone(), calldata]);
+}
@@ -0,0 +1,200 @@
+//! The JSON schema of [`GhostnetState`].
+//!
+//! The serialized form of a fully populated state is compared with
+//! `tests/snapshots/ghostnet_state_v2.json`, so a renamed field or changed
+//! encoding shows up as a snapshot diff. Run with `UPDATE_SNAPSHOTS=1` to
+//! accept a new shape, and bump the schema version if old states no longer
+//! read. `ghostnet_state_v1.json` is a state as version 1 wrote it and is
+//! never updated.
+
+#![allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::pedantic,
+    clippy::nursery
+)]
+
+use std::path::PathBuf;
+
+use alloy::primitives::{Address, B256, Bytes, I256, U256};
+use fleet_core::wallet::WalletState;
+use ghostnet_actions::state::{HashCrashRound, STATE_SCHEMA_VERSION};
+use ghostnet_actions::{
+    ActiveBoost, BetOutcome, BetRecord, BoostOffer, BoostType, CullingRisk, ExitToll,
+    GhostnetError, GhostnetState, Level, PLUGIN_ID, PnlLedger, Position, ResetTimer,
+    TransferTax,
+};
+
+const DATA: u64 = 1_000_000_000_000_000_000;
+
+fn snapshot_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/snapshots")
+        .join(format!("{name}.json"))
+}
+
+fn read_snapshot(name: &str) -> serde_json::Value {
+    let path = snapshot_path(name);
+    let json = std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("no snapshot at {}: {e}", path.display()));
+    serde_json::from_str(&json).unwrap()
+}
+
+/// A state with every field set.
+fn populated_state() -> GhostnetState {
+    GhostnetState {
+        position: Some(Position {
+            amount: U256::from(150) * U256::from(DATA),
+            level: Level::Subnet,
+            entry_timestamp: 1_700_000_000,
+            last_add_timestamp: 1_700_003_600,
+            alive: true,
+            ghost_streak: 3,
+            pending_rewards: U256::from(DATA),
+            effective_death_rate_bps: 2250,
+            in_lock_period: false,
+            active_boosts: vec![ActiveBoost {
+                boost_type: BoostType::DeathReduction,
+                value_bps: 250,
+                expiry: 1_700_086_400,
+            }],
+        }),
+        data_balance: U256::from(40) * U256::from(DATA),
+        ghost_core_allowance: U256::MAX,
+        arcade_core_allowance: U256::from(10) * U256::from(DATA),
+        hashcrash_round: Some(HashCrashRound {
+            round_id: 12,
+            is_betting: true,
+            betting_ends_at: 1_700_007_200,
+            player_count: 8,
+            prize_pool: U256::from(80) * U256::from(DATA),
+        }),
+        pnl: PnlLedger {
+            total_wagered: U256::from(5) * U256::from(DATA),
+            total_won: U256::from(12) * U256::from(DATA),
+            total_lost: U256::ZERO,
+            net: I256::try_from(7 * DATA).unwrap(),
+            bets: vec![BetRecord {
+                round_id: 11,
+                amount: U256::from(5) * U256::from(DATA),
+                target_multiplier: 250,
+                placed_at: 1_700_005_000,
+                outcome: BetOutcome::Won,
+                payout: U256::from(12) * U256::from(DATA),
+            }],
+        },
+        boost_offers: vec![BoostOffer {
+            boost_type: BoostType::YieldMultiplier,
+            value_bps: 500,
+            expiry: 1_700_090_000,
+            nonce: B256::repeat_byte(0x42),
+            signature: Bytes::from(vec![0xAB; 65]),
+            cost: U256::from(DATA),
+        }],
+        boost_spent: U256::from(2) * U256::from(DATA),
+        exit_toll: ExitToll { penalty_bps: 1000 },
+        transfer_tax: TransferTax {
+            ghost_core_bps: 0,
+            arcade_core_bps: 1000,
+        },
+        culling_risk: CullingRisk {
+            risk_bps: 1200,
+            eligible: true,
+            capacity_bps: 9500,
+        },
+        extracted: U256::from(20) * U256::from(DATA),
+        reset_timer: Some(ResetTimer {
+            deadline: 1_700_100_000,
+            epoch: 4,
+            penalty_bps: 1000,
+        }),
+        reset_epoch: Some(4),
+        last_refresh: 1_700_006_000,
+        ..GhostnetState::default()
+    }
+}
+
+#[test]
+fn serialized_state_matches_snapshot() {
+    let actual = populated_state().to_value().unwrap();
+    let path = snapshot_path("ghostnet_state_v2");
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        let json = serde_json::to_string_pretty(&actual).unwrap();
+        std::fs::write(&path, json + "\n").unwrap();
+        return;
+    }
+    assert_eq!(
+        actual,
+        read_snapshot("ghostnet_state_v2"),
+        "GhostnetState JSON changed, rerun with UPDATE_SNAPSHOTS=1 to accept it"
+    );
+}
+
+#[test]
+fn snapshot_reads_back() {
+    let state = GhostnetState::from_value(read_snapshot("ghostnet_state_v2")).unwrap();
+    assert_eq!(
+        state.to_value().unwrap(),
+        read_snapshot("ghostnet_state_v2")
+    );
+}
+
+#[test]
+fn v1_state_reads_as_current() {
+    let v1 = read_snapshot("ghostnet_state_v1");
+    assert!(v1.get("schema_version").is_none());
+
+    let state = GhostnetState::from_value(v1.clone()).unwrap();
+    assert_eq!(state.schema_version, STATE_SCHEMA_VERSION);
+    let position = state.position.unwrap();
+    assert_eq!(position.level, Level::Mainframe);
+    assert_eq!(position.amount, U256::from(100) * U256::from(DATA));
+    assert_eq!(state.data_balance, U256::from(25) * U256::from(DATA));
+    // Fields version 1 did not write take their defaults
+    assert_eq!(state.pnl, PnlLedger::default());
+    assert_eq!(state.reset_timer, None);
+
+    // The same through the versioned wallet state
+    let mut wallet = WalletState::new("test".into(), Address::ZERO);
+    wallet.set_raw_plugin_state(PLUGIN_ID, v1, 1).unwrap();
+    let state: GhostnetState = wallet.get_plugin_state(PLUGIN_ID).unwrap().unwrap();
+    assert_eq!(state.schema_version, STATE_SCHEMA_VERSION);
+    assert_eq!(state.last_refresh, 1_700_000_500);
+}
+
+#[test]
+fn unknown_fields_are_ignored() {
+    let mut value = populated_state().to_value().unwrap();
+    value["added_later"] = serde_json::json!({ "anything": true });
+    let state = GhostnetState::from_value(value).unwrap();
+    assert_eq!(state.last_refresh, 1_700_006_000);
+}
+
+#[test]
+fn errors_name_the_field() {
+    let field = |value: serde_json::Value| match GhostnetState::from_value(value) {
+        Err(GhostnetError::InvalidState { field, .. }) => field,
+        other => panic!("unexpected result: {other:?}"),
+    };
+
+    let mut value = populated_state().to_value().unwrap();
+    value["position"]["amount"] = serde_json::json!("lots");
+    assert_eq!(field(value), "position.amount");
+
+    let mut value = populated_state().to_value().unwrap();
+    value["pnl"]["bets"][0]["outcome"] = serde_json::json!("maybe");
+    assert_eq!(field(value), "pnl.bets[0].outcome");
+
+    let mut value = populated_state().to_value().unwrap();
+    value["exit_toll"]["penalty_bps"] = serde_json::json!(20_000);
+    assert_eq!(field(value), "exit_toll.penalty_bps");
+
+    let mut value = populated_state().to_value().unwrap();
+    value["transfer_tax"]["arcade_core_bps"] = serde_json::json!(20_000);
+    assert_eq!(field(value), "transfer_tax.arcade_core_bps");
+
+    let mut value = populated_state().to_value().unwrap();
+    value["schema_version"] = serde_json::json!(STATE_SCHEMA_VERSION + 1);
+    assert_eq!(field(value), "schema_version");
+}
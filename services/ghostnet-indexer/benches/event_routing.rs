@@ -0,0 +1,62 @@
+//! Benchmark topic0 resolution for the event router.
+//!
+//! Compares the lookup table behind [`EventKind::from_topic`] with matching
+//! each signature hash in turn, as the router used to, on a synthetic batch
+//! of 100k logs spread evenly over all 27 events plus unknown topics.
+//!
+//! Run with: `cargo bench -p ghostnet-indexer --bench event_routing`
+
+#![allow(missing_docs)]
+
+use std::hint::black_box;
+
+use alloy::primitives::B256;
+use criterion::{Criterion, Throughput, criterion_group, criterion_main};
+use ghostnet_indexer::indexer::EventKind;
+
+const BATCH_SIZE: usize = 100_000;
+
+/// topic0 of every log in the batch; one in 28 is unknown.
+fn synthetic_topics() -> Vec<B256> {
+    (0..BATCH_SIZE)
+        .map(|i| {
+            EventKind::ALL
+                .get(i % (EventKind::ALL.len() + 1))
+                .map_or(B256::repeat_byte(0xFF), |kind| kind.signature_hash())
+        })
+        .collect()
+}
+
+/// Resolve a topic by comparing it against each signature in turn.
+fn sequential_match(topic0: &B256) -> Option<EventKind> {
+    EventKind::ALL
+        .into_iter()
+        .find(|kind| kind.signature_hash().as_slice() == topic0.as_slice())
+}
+
+fn topic_resolution(c: &mut Criterion) {
+    let topics = synthetic_topics();
+
+    let mut group = c.benchmark_group("topic_resolution");
+    group.throughput(Throughput::Elements(BATCH_SIZE as u64));
+    group.bench_function("sequential", |b| {
+        b.iter(|| {
+            topics
+                .iter()
+                .filter_map(|t| sequential_match(black_box(t)))
+                .count()
+        });
+    });
+    group.bench_function("lookup_table", |b| {
+        b.iter(|| {
+            topics
+                .iter()
+                .filter_map(|t| EventKind::from_topic(black_box(t)))
+                .count()
+        });
+    });
+    group.finish();
+}
+
+criterion_group!(benches, topic_resolution);
+criterion_main!(benches);
@@ -0,0 +1,107 @@
+//! Benchmark ingestion writes with and without write batching.
+//!
+//! Replays the store writes of a synthetic stretch of blocks, each block
+//! holding a position change with its history, a death and a token transfer
+//! per event, followed by the checkpoint. Per-event ingestion commits every
+//! write on its own; batched ingestion commits each block in one transaction.
+//!
+//! Needs Docker, for the same TimescaleDB container as the integration tests.
+//!
+//! Run with: `cargo bench -p ghostnet-indexer --bench store_batching`
+
+#![allow(
+    missing_docs,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::pedantic,
+    clippy::nursery,
+    dead_code, // Not every shared fixture is benchmarked
+)]
+
+// The fixtures of the integration tests, whose unit tests are built here too
+#[path = "../tests/common/mod.rs"]
+#[allow(unused_imports)]
+mod common;
+
+use alloy::primitives::B256;
+use chrono::Utc;
+use criterion::{Criterion, Throughput, criterion_group, criterion_main};
+use tokio::runtime::Runtime;
+
+use common::fixtures::{TestDb, death_fixtures, position_fixtures};
+use ghostnet_indexer::ports::{DeathStore, IndexerStateStore, PositionStore, TokenFlowStore};
+use ghostnet_indexer::store::PostgresStore;
+use ghostnet_indexer::types::entities::{PositionAction, PositionHistoryEntry, TokenTransfer};
+use ghostnet_indexer::types::enums::Level;
+use ghostnet_indexer::types::primitives::{BlockNumber, TokenAmount};
+
+const BLOCKS: u64 = 20;
+const EVENTS_PER_BLOCK: u64 = 50;
+const USER: &str = "0x1111111111111111111111111111111111111111";
+
+/// Write the events of `block`, then checkpoint it.
+async fn ingest_block(store: &PostgresStore, block: u64) {
+    for event in 0..EVENTS_PER_BLOCK {
+        let position = position_fixtures::create_test_position(USER, Level::Subnet);
+        let entry = PositionHistoryEntry::new(
+            &position,
+            PositionAction::JackedIn,
+            position.amount.clone(),
+            BlockNumber::new(block),
+            position.entry_timestamp,
+        );
+        store
+            .save_position_with_history(&position, &entry, &[])
+            .await
+            .unwrap();
+        store
+            .record_deaths(&[death_fixtures::create_test_death(USER, Level::Subnet, 1)])
+            .await
+            .unwrap();
+        store
+            .record_transfer(&TokenTransfer {
+                from_address: position.user_address,
+                to_address: position.user_address,
+                amount: TokenAmount::zero(),
+                block_number: BlockNumber::new(block),
+                tx_hash: B256::left_padding_from(&event.to_be_bytes()).0,
+                log_index: event,
+                timestamp: Utc::now(),
+            })
+            .await
+            .unwrap();
+    }
+    store
+        .set_last_block(BlockNumber::new(block), B256::ZERO)
+        .await
+        .unwrap();
+}
+
+fn ingestion(c: &mut Criterion) {
+    let runtime = Runtime::new().expect("Failed to start runtime");
+    let db = runtime.block_on(TestDb::new());
+    let batched = db.store.batched();
+
+    let mut group = c.benchmark_group("ingestion");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(BLOCKS * EVENTS_PER_BLOCK));
+    group.bench_function("per_event", |b| {
+        b.to_async(&runtime).iter(|| async {
+            for block in 0..BLOCKS {
+                ingest_block(&db.store, block).await;
+            }
+        });
+    });
+    group.bench_function("batched", |b| {
+        b.to_async(&runtime).iter(|| async {
+            for block in 0..BLOCKS {
+                ingest_block(&batched, block).await;
+                batched.commit_batch().await.unwrap();
+            }
+        });
+    });
+    group.finish();
+}
+
+criterion_group!(benches, ingestion);
+criterion_main!(benches);
@@ -0,0 +1,11 @@
+-- Level stats: ghost streak materialization
+--
+-- Adds the running sum of ghost streaks across active positions so the
+-- average streak per level can be served without scanning `positions`.
+-- Maintained incrementally by the stats aggregator and rebuilt by
+-- `ghostnet-indexer recompute-stats`.
+
+ALTER TABLE level_stats
+    ADD COLUMN IF NOT EXISTS total_ghost_streak BIGINT NOT NULL DEFAULT 0;
+
+COMMENT ON COLUMN level_stats.total_ghost_streak IS 'Sum of ghost streaks across active positions';
@@ -0,0 +1,17 @@
+-- Leaderboard indexes
+--
+-- Leaderboards break ties by ascending wallet address, so the address is part
+-- of each sort key. This lets the top-N queries walk an index in order instead
+-- of sorting every candidate row.
+
+-- Ghost streak leaderboard: replaces the streak-only index from schema v2
+DROP INDEX IF EXISTS idx_positions_ghost_streak;
+CREATE INDEX idx_positions_ghost_streak
+    ON positions(ghost_streak DESC, user_address ASC)
+    WHERE is_alive = TRUE;
+
+-- Extraction leaderboards: per-wallet totals and each wallet's largest extraction
+CREATE INDEX IF NOT EXISTS idx_positions_extracted_user
+    ON positions(user_address, extracted_amount DESC)
+    INCLUDE (level)
+    WHERE is_extracted = TRUE;
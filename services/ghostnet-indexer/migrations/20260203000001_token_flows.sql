@@ -0,0 +1,75 @@
+-- Token flow analytics
+--
+-- Raw transfers are only written when `token_flows.persist_transfers` is
+-- enabled. The hourly aggregates are always maintained by the stats
+-- aggregator and back the burn rate and per-address flow queries.
+
+-- ═══════════════════════════════════════════════════════════════════════════════
+-- TOKEN TRANSFERS (Hypertable - Very high volume, append-only)
+-- ═══════════════════════════════════════════════════════════════════════════════
+
+CREATE TABLE token_transfers (
+    tx_hash             BYTEA NOT NULL,
+    log_index           INTEGER NOT NULL,
+    from_address        BYTEA NOT NULL,               -- Zero address for mints
+    to_address          BYTEA NOT NULL,               -- Dead address for burns
+    amount              NUMERIC(78, 0) NOT NULL,
+    block_number        BIGINT NOT NULL,
+    timestamp           TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (timestamp, tx_hash, log_index),
+
+    CONSTRAINT chk_transfer_amount_positive CHECK (amount >= 0)
+);
+
+SELECT create_hypertable('token_transfers', 'timestamp',
+    chunk_time_interval => INTERVAL '1 day',
+    if_not_exists => TRUE);
+
+ALTER TABLE token_transfers SET (
+    timescaledb.compress,
+    timescaledb.compress_segmentby = 'from_address',
+    timescaledb.compress_orderby = 'timestamp DESC'
+);
+
+SELECT add_compression_policy('token_transfers', INTERVAL '1 day',
+    if_not_exists => TRUE);
+
+-- Retention: raw transfers are for debugging and short-term analysis
+SELECT add_retention_policy('token_transfers', INTERVAL '90 days',
+    if_not_exists => TRUE);
+
+CREATE INDEX idx_token_transfers_from ON token_transfers(from_address, timestamp DESC);
+CREATE INDEX idx_token_transfers_to ON token_transfers(to_address, timestamp DESC);
+
+COMMENT ON TABLE token_transfers IS 'Raw DATA transfers (optional, see token_flows.persist_transfers)';
+
+-- ═══════════════════════════════════════════════════════════════════════════════
+-- HOURLY AGGREGATES
+-- ═══════════════════════════════════════════════════════════════════════════════
+
+CREATE TABLE token_flow_hourly (
+    bucket              TIMESTAMPTZ PRIMARY KEY,
+    burned              NUMERIC(78, 0) NOT NULL DEFAULT 0,   -- Includes tax burns
+    tax_burned          NUMERIC(78, 0) NOT NULL DEFAULT 0,
+    tax_collected       NUMERIC(78, 0) NOT NULL DEFAULT 0,
+    volume              NUMERIC(78, 0) NOT NULL DEFAULT 0,
+    transfer_count      BIGINT NOT NULL DEFAULT 0
+);
+
+COMMENT ON TABLE token_flow_hourly IS 'Protocol-wide DATA burn, tax and volume per hour';
+
+CREATE TABLE address_flow_hourly (
+    address             BYTEA NOT NULL,
+    bucket              TIMESTAMPTZ NOT NULL,
+    inflow              NUMERIC(78, 0) NOT NULL DEFAULT 0,
+    outflow             NUMERIC(78, 0) NOT NULL DEFAULT 0,
+    transfers_in        BIGINT NOT NULL DEFAULT 0,
+    transfers_out       BIGINT NOT NULL DEFAULT 0,
+    PRIMARY KEY (address, bucket)
+);
+
+SELECT create_hypertable('address_flow_hourly', 'bucket',
+    chunk_time_interval => INTERVAL '7 days',
+    if_not_exists => TRUE);
+
+COMMENT ON TABLE address_flow_hourly IS 'DATA inflow and outflow per address per hour';
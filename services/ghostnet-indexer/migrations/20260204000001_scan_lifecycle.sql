@@ -0,0 +1,11 @@
+-- Scan lifecycle: finalization latency
+--
+-- `finalization_latency_ms` is the time between `ScanExecuted` and
+-- `ScanFinalized`, computed by the scan handler on finalization. It stays
+-- NULL for scans that never finalize and for placeholder scans created
+-- before their `ScanExecuted` event was seen (seed = 'unknown').
+
+ALTER TABLE scans
+    ADD COLUMN IF NOT EXISTS finalization_latency_ms BIGINT;
+
+COMMENT ON COLUMN scans.finalization_latency_ms IS 'Milliseconds from execution to finalization';
@@ -0,0 +1,26 @@
+-- Position history: level and ghost streak per entry
+--
+-- Every entry records the level and ghost streak of its position after the
+-- change, so a user's timeline can be read without joining positions.
+-- Existing entries take the values of their position's current row.
+--
+-- History is paged newest first by (timestamp, id). The user index gains `id`
+-- as a tiebreaker for entries with the same block timestamp.
+
+ALTER TABLE position_history
+    ADD COLUMN IF NOT EXISTS level SMALLINT,
+    ADD COLUMN IF NOT EXISTS ghost_streak INTEGER;
+
+UPDATE position_history h
+SET level = p.level,
+    ghost_streak = p.ghost_streak
+FROM positions p
+WHERE h.position_id = p.id
+  AND h.level IS NULL;
+
+COMMENT ON COLUMN position_history.level IS 'Level of the position';
+COMMENT ON COLUMN position_history.ghost_streak IS 'Ghost streak after the change';
+
+DROP INDEX IF EXISTS idx_position_history_user;
+CREATE INDEX IF NOT EXISTS idx_position_history_user
+    ON position_history(user_address, timestamp DESC, id DESC);
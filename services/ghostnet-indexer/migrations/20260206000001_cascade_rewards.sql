@@ -0,0 +1,55 @@
+-- Cascade rewards: per-survivor shares of each cascade distribution
+--
+-- `CascadeDistributed` only carries totals. The death handler splits them
+-- between the positions alive at the time, pro rata to stake, and records one
+-- row per receiving position. A position receives at most one share per
+-- distribution, so replayed events are ignored by the primary key.
+--
+-- Rows are linked to their scan through `scans (level, finalized_block)`:
+-- cascades are distributed by `finalizeScan`, in the same transaction as
+-- `ScanFinalized`.
+
+-- ═══════════════════════════════════════════════════════════════════════════════
+-- CASCADE REWARDS (Hypertable - append-only)
+-- ═══════════════════════════════════════════════════════════════════════════════
+
+CREATE TABLE cascade_rewards (
+    id                  UUID NOT NULL,
+    position_id         UUID NOT NULL,
+    user_address        BYTEA NOT NULL,
+    source_level        SMALLINT NOT NULL,            -- Level where deaths occurred
+    level               SMALLINT NOT NULL,            -- Level of the receiving position
+    amount              NUMERIC(78, 0) NOT NULL,
+    block_number        BIGINT NOT NULL,
+    created_at          TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (created_at, block_number, source_level, position_id),
+
+    CONSTRAINT chk_cascade_source_level CHECK (source_level >= 1 AND source_level <= 5),
+    CONSTRAINT chk_cascade_level CHECK (level >= 1 AND level <= source_level),
+    CONSTRAINT chk_cascade_amount_positive CHECK (amount >= 0)
+);
+
+SELECT create_hypertable('cascade_rewards', 'created_at',
+    chunk_time_interval => INTERVAL '7 days',
+    if_not_exists => TRUE);
+
+ALTER TABLE cascade_rewards SET (
+    timescaledb.compress,
+    timescaledb.compress_segmentby = 'user_address',
+    timescaledb.compress_orderby = 'created_at DESC'
+);
+
+SELECT add_compression_policy('cascade_rewards', INTERVAL '7 days',
+    if_not_exists => TRUE);
+
+-- No retention policy: all-time totals feed the cascade earnings leaderboard
+
+CREATE INDEX idx_cascade_rewards_user ON cascade_rewards(user_address, created_at DESC);
+CREATE INDEX idx_cascade_rewards_scan ON cascade_rewards(source_level, block_number);
+
+COMMENT ON TABLE cascade_rewards IS 'Survivor shares of cascade distributions (hypertable)';
+
+-- Scans are looked up by the block they finalized in
+CREATE INDEX IF NOT EXISTS idx_scans_finalized_block
+    ON scans(level, finalized_block)
+    WHERE finalized_block IS NOT NULL;
@@ -0,0 +1,32 @@
+-- Position survival: how long and through how many scans positions lasted
+--
+-- Every exit (extraction, cull, trace, system reset) records the seconds
+-- between entry and exit and the number of finalized scans at the position's
+-- level in between. Both stay NULL for exits whose entry was never indexed,
+-- e.g. when a backfill starts mid-lifecycle.
+--
+-- Level stats gain the running totals needed to serve the average survival
+-- time and the cull rate without scanning `positions`. They are maintained
+-- incrementally by the stats aggregator and rebuilt by
+-- `ghostnet-indexer recompute-stats`.
+
+ALTER TABLE positions
+    ADD COLUMN IF NOT EXISTS survival_seconds BIGINT,
+    ADD COLUMN IF NOT EXISTS scans_survived INTEGER;
+
+COMMENT ON COLUMN positions.survival_seconds IS 'Seconds from entry to exit (NULL if the entry was not indexed)';
+COMMENT ON COLUMN positions.scans_survived IS 'Finalized scans survived before exit (NULL if the entry was not indexed)';
+
+-- Survival analytics group exits by level
+CREATE INDEX IF NOT EXISTS idx_positions_level_exit
+    ON positions(level, exit_reason)
+    WHERE exit_reason IS NOT NULL;
+
+ALTER TABLE level_stats
+    ADD COLUMN IF NOT EXISTS total_culled INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS total_survival_seconds BIGINT NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS survival_samples INTEGER NOT NULL DEFAULT 0;
+
+COMMENT ON COLUMN level_stats.total_culled IS 'Positions culled by level capacity';
+COMMENT ON COLUMN level_stats.total_survival_seconds IS 'Sum of survival times of exited positions';
+COMMENT ON COLUMN level_stats.survival_samples IS 'Exited positions with a known survival time';
@@ -0,0 +1,36 @@
+-- Per-contract indexing progress
+--
+-- indexer_state holds one cursor per chain. A contract enabled later (or
+-- re-enabled after being disabled) needs its history indexed without
+-- reprocessing the others, so each contract gets its own cursor: the last
+-- block up to which all of its logs are indexed.
+--
+-- Existing deployments indexed every contract up to the chain cursor, so all
+-- contracts start from it. A contract that was disabled can be re-indexed
+-- with `ghostnet-indexer backfill --contract <name> --from <block>`.
+
+CREATE TABLE IF NOT EXISTS contract_cursors (
+    chain_id            BIGINT NOT NULL,
+    contract            TEXT NOT NULL,
+    last_block          BIGINT NOT NULL,
+    updated_at          TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (chain_id, contract)
+);
+
+COMMENT ON TABLE contract_cursors IS 'Indexer progress per contract, one row per chain and contract';
+COMMENT ON COLUMN contract_cursors.contract IS 'Contract configuration key, e.g. dead_pool';
+COMMENT ON COLUMN contract_cursors.last_block IS 'Last block up to which all logs of the contract are indexed';
+
+INSERT INTO contract_cursors (chain_id, contract, last_block)
+SELECT s.chain_id, c.contract, s.last_block
+FROM indexer_state s
+CROSS JOIN (VALUES
+    ('ghost_core'),
+    ('trace_scan'),
+    ('dead_pool'),
+    ('data_token'),
+    ('fee_router'),
+    ('rewards_distributor')
+) AS c(contract)
+WHERE s.last_block > 0
+ON CONFLICT DO NOTHING;
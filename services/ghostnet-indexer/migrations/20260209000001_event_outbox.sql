@@ -0,0 +1,38 @@
+-- Transactional event outbox
+--
+-- Handlers write the events they want published in the same transaction as
+-- the state change the events describe. The outbox relay publishes the rows
+-- afterwards and marks them published, so a crash between commit and publish
+-- delays events instead of losing them, and rolled back changes publish
+-- nothing.
+--
+-- Rows are published in id order per aggregate (e.g. `position:0x...`,
+-- `round:42`). A row waiting for a retry holds back later rows of its
+-- aggregate only. Published rows are deleted after the relay's retention.
+
+CREATE TABLE IF NOT EXISTS event_outbox (
+    id                  BIGSERIAL PRIMARY KEY,
+    topic               TEXT NOT NULL,
+    aggregate           TEXT NOT NULL,
+    event_type          TEXT NOT NULL,
+    payload             BYTEA NOT NULL,
+    block_number        BIGINT NOT NULL,
+    attempts            INTEGER NOT NULL DEFAULT 0,
+    next_attempt_at     TIMESTAMPTZ,
+    last_error          TEXT,
+    created_at          TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    published_at        TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_event_outbox_unpublished
+    ON event_outbox (aggregate, id)
+    WHERE published_at IS NULL;
+
+CREATE INDEX IF NOT EXISTS idx_event_outbox_published_at
+    ON event_outbox (published_at)
+    WHERE published_at IS NOT NULL;
+
+COMMENT ON TABLE event_outbox IS 'Events committed with their state change, awaiting publication to Iggy';
+COMMENT ON COLUMN event_outbox.aggregate IS 'Entity the event belongs to; events of one aggregate are published in id order';
+COMMENT ON COLUMN event_outbox.payload IS 'Serialized event, published as is';
+COMMENT ON COLUMN event_outbox.next_attempt_at IS 'Earliest retry after a failed publish';
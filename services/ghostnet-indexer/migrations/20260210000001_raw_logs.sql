@@ -0,0 +1,39 @@
+-- Raw log archive and replay progress
+--
+-- With `raw_logs.enabled`, the pipeline archives every log it receives, still
+-- undecoded, in the same batch as the handlers' writes. `ghostnet-indexer
+-- replay` feeds the archived logs of a block range through the handlers
+-- again, so a fixed handler can rebuild its tables without refetching the
+-- range from RPC.
+--
+-- Logs past a reorg's fork point are deleted with the rest of the orphaned
+-- blocks. The archive is kept for as long as the indexed history.
+
+CREATE TABLE IF NOT EXISTS raw_logs (
+    block_number        BIGINT NOT NULL,
+    tx_index            BIGINT NOT NULL,
+    log_index           BIGINT NOT NULL,
+    block_hash          BYTEA NOT NULL,
+    tx_hash             BYTEA NOT NULL,
+    address             BYTEA NOT NULL,
+    topics              BYTEA NOT NULL,
+    data                BYTEA NOT NULL,
+    timestamp           TIMESTAMPTZ NOT NULL,
+    tx_function         TEXT,
+    tx_from             BYTEA,
+    PRIMARY KEY (block_number, tx_index, log_index)
+);
+
+COMMENT ON TABLE raw_logs IS 'Undecoded logs as received from RPC, replayed by `ghostnet-indexer replay`';
+COMMENT ON COLUMN raw_logs.topics IS 'Topics concatenated, 32 bytes each';
+COMMENT ON COLUMN raw_logs.tx_function IS 'GHOSTNET function of the transaction, if transaction context was enabled';
+
+-- One row per replay still in progress, removed once it finishes
+CREATE TABLE IF NOT EXISTS replay_progress (
+    replay              TEXT PRIMARY KEY,
+    last_block          BIGINT NOT NULL,
+    updated_at          TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+COMMENT ON COLUMN replay_progress.replay IS 'Block range and handlers of the replay, e.g. 100-200:position,scan';
+COMMENT ON COLUMN replay_progress.last_block IS 'Last block whose logs have all been replayed';
@@ -0,0 +1,19 @@
+-- Position listing indexes
+--
+-- `GET /positions` lists positions newest first and pages with a keyset
+-- cursor on (entry_timestamp, id), so each page is an index range scan
+-- rather than an OFFSET over every earlier row. The other filters (stake,
+-- streak, active) are checked on the rows walked in this order.
+
+-- All positions, and positions entered after a time
+CREATE INDEX IF NOT EXISTS idx_positions_entry_id
+    ON positions(entry_timestamp DESC, id DESC);
+
+-- Positions at a level
+CREATE INDEX IF NOT EXISTS idx_positions_level_entry_id
+    ON positions(level, entry_timestamp DESC, id DESC);
+
+-- Active positions, the common dashboard listing
+CREATE INDEX IF NOT EXISTS idx_positions_active_entry_id
+    ON positions(entry_timestamp DESC, id DESC)
+    WHERE is_alive = TRUE AND is_extracted = FALSE;
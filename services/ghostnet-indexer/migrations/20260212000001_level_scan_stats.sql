@@ -0,0 +1,62 @@
+-- Per-level scan history
+--
+-- One row per finalized scan with the level's deaths, survivors and stake
+-- right after it, written by the stats aggregator. Backs the bucketed
+-- death rate series of `GET /stats/levels/:level/history`.
+
+-- ═══════════════════════════════════════════════════════════════════════════════
+-- LEVEL SCAN STATS (Hypertable - one row per scan, append-only)
+-- ═══════════════════════════════════════════════════════════════════════════════
+
+CREATE TABLE level_scan_stats (
+    scanned_at          TIMESTAMPTZ NOT NULL,         -- Scan finalization time
+    scan_id             VARCHAR(78) NOT NULL,         -- On-chain U256 as string
+    level               SMALLINT NOT NULL,
+    deaths              INTEGER NOT NULL,
+    survivors           INTEGER NOT NULL,             -- Alive at the level after the scan
+    total_staked        NUMERIC(78, 0) NOT NULL,      -- Staked at the level after the scan
+    block_number        BIGINT,                       -- Finalization block, NULL if unknown
+    PRIMARY KEY (scanned_at, scan_id),
+
+    CONSTRAINT chk_level_scan_level CHECK (level >= 1 AND level <= 5),
+    CONSTRAINT chk_level_scan_counts CHECK (deaths >= 0 AND survivors >= 0)
+);
+
+SELECT create_hypertable('level_scan_stats', 'scanned_at',
+    chunk_time_interval => INTERVAL '7 days',
+    if_not_exists => TRUE);
+
+CREATE INDEX idx_level_scan_stats_level ON level_scan_stats(level, scanned_at DESC);
+-- Reorg rollback deletes by block
+CREATE INDEX idx_level_scan_stats_block ON level_scan_stats(block_number);
+
+COMMENT ON TABLE level_scan_stats IS 'Deaths, survivors and stake of a level after each finalized scan';
+
+-- ═══════════════════════════════════════════════════════════════════════════════
+-- BACKFILL
+-- ═══════════════════════════════════════════════════════════════════════════════
+
+-- Survivors and stake of past scans are reconstructed from positions:
+-- those entered by the finalization and not exited by then. Stake uses each
+-- position's current amount, so it is approximate for positions that added
+-- stake later. Deaths are the scan's own count.
+INSERT INTO level_scan_stats (
+    scanned_at, scan_id, level, deaths, survivors, total_staked, block_number
+)
+SELECT
+    s.finalized_at,
+    s.scan_id,
+    s.level,
+    COALESCE(s.death_count, 0),
+    COUNT(p.id),
+    COALESCE(SUM(p.amount), 0),
+    s.finalized_block
+FROM scans s
+LEFT JOIN positions p
+    ON p.level = s.level
+   AND p.entry_timestamp <= s.finalized_at
+   AND (p.exit_timestamp IS NULL OR p.exit_timestamp > s.finalized_at)
+   AND (p.exit_reason IS NULL OR p.exit_reason <> 'Superseded')
+WHERE s.finalized_at IS NOT NULL
+GROUP BY s.scan_id, s.finalized_at, s.level, s.death_count, s.finalized_block
+ON CONFLICT (scanned_at, scan_id) DO NOTHING;
@@ -0,0 +1,73 @@
+-- DeadPool prediction market: rounds and bets
+--
+-- Each bet is stored with the odds at the moment it was placed: the round's
+-- pools right before it, the probability they gave its side, and the payout
+-- multiple it would have got had the round closed right after it. These
+-- cannot be reconstructed later without replaying every earlier bet of the
+-- round in chain order. Resolved rounds get the payout multiple of their
+-- winners and the house take.
+--
+-- Multiples and probabilities are in basis points (10 000 = 1x or 100%),
+-- rounded down like the contract's own odds.
+
+-- ═══════════════════════════════════════════════════════════════════════════════
+-- ROUNDS (Regular Table - entities with updates)
+-- ═══════════════════════════════════════════════════════════════════════════════
+
+CREATE TABLE rounds (
+    id                  UUID PRIMARY KEY,
+    round_id            VARCHAR(78) NOT NULL UNIQUE,  -- On-chain U256 as string
+    round_type          SMALLINT NOT NULL,
+    target_level        SMALLINT,                     -- NULL for global rounds
+    line                NUMERIC(78, 0) NOT NULL,
+    deadline            TIMESTAMPTZ NOT NULL,
+    over_pool           NUMERIC(78, 0) NOT NULL DEFAULT 0,
+    under_pool          NUMERIC(78, 0) NOT NULL DEFAULT 0,
+    is_resolved         BOOLEAN NOT NULL DEFAULT FALSE,
+    outcome             BOOLEAN,                      -- TRUE = OVER won
+    resolve_time        TIMESTAMPTZ,
+    total_burned        NUMERIC(78, 0),
+    payout_multiple_bps BIGINT,                       -- NULL if nobody won
+    house_take          NUMERIC(78, 0),               -- Rake, or the pot if nobody won
+
+    CONSTRAINT chk_round_type CHECK (round_type >= 0 AND round_type <= 3),
+    CONSTRAINT chk_round_target_level CHECK (target_level >= 1 AND target_level <= 5),
+    CONSTRAINT chk_round_pools CHECK (over_pool >= 0 AND under_pool >= 0)
+);
+
+CREATE INDEX idx_rounds_active ON rounds(deadline) WHERE NOT is_resolved;
+
+COMMENT ON TABLE rounds IS 'DeadPool betting rounds';
+
+-- ═══════════════════════════════════════════════════════════════════════════════
+-- BETS (Regular Table - claims update rows)
+-- ═══════════════════════════════════════════════════════════════════════════════
+
+CREATE TABLE bets (
+    id                      UUID PRIMARY KEY,
+    round_id                UUID NOT NULL REFERENCES rounds(id) ON DELETE CASCADE,
+    user_address            BYTEA NOT NULL,
+    amount                  NUMERIC(78, 0) NOT NULL,
+    is_over                 BOOLEAN NOT NULL,
+    block_number            BIGINT NOT NULL,
+    log_index               BIGINT NOT NULL,
+    placed_at               TIMESTAMPTZ NOT NULL,
+    over_pool_before        NUMERIC(78, 0) NOT NULL,
+    under_pool_before       NUMERIC(78, 0) NOT NULL,
+    implied_probability_bps INTEGER,                  -- NULL for a round's first bet
+    payout_multiple_bps     BIGINT,
+    is_claimed              BOOLEAN NOT NULL DEFAULT FALSE,
+    winnings                NUMERIC(78, 0),
+    claimed_at              TIMESTAMPTZ,
+
+    -- One BetPlaced log is one bet; replayed logs are ignored
+    CONSTRAINT uq_bets_log UNIQUE (round_id, block_number, log_index),
+    CONSTRAINT chk_bet_amount_positive CHECK (amount > 0),
+    CONSTRAINT chk_bet_probability CHECK (
+        implied_probability_bps >= 0 AND implied_probability_bps <= 10000
+    )
+);
+
+CREATE INDEX idx_bets_user ON bets(user_address, placed_at DESC);
+
+COMMENT ON TABLE bets IS 'DeadPool bets with the odds at the time they were placed';
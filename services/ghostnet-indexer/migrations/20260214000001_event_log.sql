@@ -0,0 +1,36 @@
+-- Sequenced event log for WebSocket replay
+--
+-- Every event published with an ID (outbox entries, derived round events)
+-- gets the next sequence of its topic before it is sent to Iggy, where it
+-- travels in the `sequence` header. The WebSocket API delivers the same
+-- sequence, so both paths agree on it. A message published again (a retry,
+-- or a relay restart between publishing and marking) keeps its sequence.
+--
+-- Published events are kept for a while so reconnecting clients can resume
+-- from the last sequence they saw. Retention deletes a prefix of each
+-- topic's sequences, always keeping its latest event.
+
+CREATE TABLE IF NOT EXISTS event_log_sequences (
+    topic               TEXT PRIMARY KEY,
+    last_sequence       BIGINT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS event_log (
+    topic               TEXT NOT NULL,
+    sequence            BIGINT NOT NULL,
+    message_id          BYTEA NOT NULL,
+    payload             BYTEA NOT NULL,
+    logged_at           TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    published_at        TIMESTAMPTZ,
+    PRIMARY KEY (topic, sequence),
+    UNIQUE (topic, message_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_event_log_published_at
+    ON event_log (topic, published_at)
+    WHERE published_at IS NOT NULL;
+
+COMMENT ON TABLE event_log_sequences IS 'Last sequence assigned per topic';
+COMMENT ON TABLE event_log IS 'Recently published events per topic, replayed to reconnecting WebSocket clients';
+COMMENT ON COLUMN event_log.message_id IS 'Stable message ID (big-endian u128); a message logged again keeps its sequence';
+COMMENT ON COLUMN event_log.published_at IS 'When Iggy accepted the event; only published events are replayed';
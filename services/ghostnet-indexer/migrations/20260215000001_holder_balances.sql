@@ -0,0 +1,39 @@
+-- DATA holder balances
+--
+-- Maintained by the token handler from `Transfer` events: each transfer
+-- debits the sender and credits the recipient, mints and burns only touch
+-- the holder side. `last_block` and `last_log_index` are the last transfer
+-- applied, so a replayed transfer is not applied twice.
+--
+-- A debit larger than the balance means a transfer was missed; the balance
+-- is clamped to zero and the holder flagged. Flagged holders, and a sample
+-- of the others, are compared with `balanceOf` by the holder reconciler.
+
+-- ═══════════════════════════════════════════════════════════════════════════════
+-- HOLDER BALANCES (Regular Table - one row per address, updated in place)
+-- ═══════════════════════════════════════════════════════════════════════════════
+
+CREATE TABLE holder_balances (
+    address                 BYTEA PRIMARY KEY,
+    balance                 NUMERIC(78, 0) NOT NULL DEFAULT 0,   -- Wei
+    first_seen              TIMESTAMPTZ NOT NULL,
+    last_active             TIMESTAMPTZ NOT NULL,
+    last_block              BIGINT NOT NULL,
+    last_log_index          INTEGER NOT NULL,
+    needs_reconciliation    BOOLEAN NOT NULL DEFAULT FALSE,
+    reconciled_at           TIMESTAMPTZ,
+
+    CONSTRAINT chk_holder_balance_positive CHECK (balance >= 0)
+);
+
+CREATE INDEX idx_holder_balances_balance ON holder_balances(balance DESC)
+    WHERE balance > 0;
+CREATE INDEX idx_holder_balances_first_seen ON holder_balances(first_seen DESC)
+    WHERE balance > 0;
+CREATE INDEX idx_holder_balances_last_active ON holder_balances(last_active DESC)
+    WHERE balance > 0;
+-- Flagged holders first, then those checked longest ago
+CREATE INDEX idx_holder_balances_reconcile
+    ON holder_balances(needs_reconciliation DESC, reconciled_at NULLS FIRST);
+
+COMMENT ON TABLE holder_balances IS 'DATA balance per address, derived from transfers';
@@ -0,0 +1,37 @@
+-- Boosts applied to positions
+--
+-- Saved by the position handler from `BoostApplied` events, one row per log,
+-- so a replayed event is not a second boost. A boost applies until its
+-- expiry or until its position closes, whichever comes first.
+--
+-- `expired_at` stays NULL until the boost expirer records the boost as
+-- expired, with the time it stopped applying, and publishes `BoostExpired`
+-- for it. Boosts of closed positions are no longer active before that.
+
+-- ═══════════════════════════════════════════════════════════════════════════════
+-- BOOSTS (Regular Table - one row per BoostApplied, expired_at set once)
+-- ═══════════════════════════════════════════════════════════════════════════════
+
+CREATE TABLE boosts (
+    id                  UUID PRIMARY KEY,
+    position_id         UUID NOT NULL,
+    user_address        BYTEA NOT NULL,               -- 20-byte Ethereum address
+    boost_type          SMALLINT NOT NULL,            -- 0 = DeathReduction, 1 = YieldMultiplier
+    value_bps           SMALLINT NOT NULL,
+    expiry              TIMESTAMPTZ NOT NULL,
+    block_number        BIGINT NOT NULL,
+    log_index           INTEGER NOT NULL,
+    created_at          TIMESTAMPTZ NOT NULL,
+    expired_at          TIMESTAMPTZ,
+
+    CONSTRAINT uq_boosts_log UNIQUE (block_number, log_index)
+);
+
+-- Active boosts of a user
+CREATE INDEX idx_boosts_user_unexpired ON boosts(user_address, expiry)
+    WHERE expired_at IS NULL;
+-- Boosts due to be recorded as expired, by expiry or by position
+CREATE INDEX idx_boosts_unexpired ON boosts(expiry) WHERE expired_at IS NULL;
+CREATE INDEX idx_boosts_position_unexpired ON boosts(position_id) WHERE expired_at IS NULL;
+
+COMMENT ON TABLE boosts IS 'Boosts applied to positions, with when they stopped applying';
@@ -0,0 +1,35 @@
+-- Chain IDs: which chain each row was indexed from
+--
+-- Every chain is indexed into a schema of its own (`public` for a single
+-- chain, `chain_<id>` by default for the `[[chains]]` of the config), so
+-- rows of two chains never share a table. Entity tables still record their
+-- chain, for exports and queries across schemas.
+--
+-- The indexer connects with `ghostnet.chain_id` set to the chain of the
+-- schema, which becomes the column default: existing rows and rows written
+-- without a chain ID get the schema's chain. Without the setting (e.g.
+-- migrations run by hand) the migration fails rather than tag the rows with
+-- a made-up chain; set it with `SET ghostnet.chain_id = '<id>'` first.
+
+DO $$
+DECLARE
+    chain BIGINT := NULLIF(current_setting('ghostnet.chain_id', true), '')::BIGINT;
+    tbl TEXT;
+BEGIN
+    IF chain IS NULL OR chain <= 0 THEN
+        RAISE EXCEPTION 'ghostnet.chain_id must be set to the chain of schema %', current_schema()
+            USING HINT = 'Run migrations through `ghostnet-indexer migrate`, or SET ghostnet.chain_id first';
+    END IF;
+    FOREACH tbl IN ARRAY ARRAY[
+        'positions', 'position_history', 'scans', 'deaths', 'level_stats', 'global_stats',
+        'rounds', 'bets', 'token_transfers', 'token_flow_hourly', 'address_flow_hourly',
+        'cascade_rewards', 'level_scan_stats', 'holder_balances', 'boosts', 'raw_logs',
+        'event_outbox', 'event_log'
+    ] LOOP
+        EXECUTE format(
+            'ALTER TABLE %I ADD COLUMN IF NOT EXISTS chain_id BIGINT NOT NULL DEFAULT %s',
+            tbl, chain
+        );
+        EXECUTE format('COMMENT ON COLUMN %I.chain_id IS %L', tbl, 'Chain the row was indexed from');
+    END LOOP;
+END $$;
@@ -0,0 +1,62 @@
+-- Quarantined events
+--
+-- A log whose handler keeps failing is set aside here instead of stopping
+-- the pipeline: the raw log as received, the handler it was routed to and
+-- the error it failed with. Indexing carries on with the next log.
+-- `ghostnet-indexer quarantine retry` routes quarantined logs again once the
+-- handler is fixed, and deletes the rows of those that go through.
+--
+-- Handlers with strict ordering quarantine every later log of an aggregate
+-- (e.g. a user's position) after one of its logs, with `blocked_by` set to
+-- the quarantined log of that aggregate before it; those are retried after
+-- the log blocking them.
+--
+-- Rows past a reorg's fork point are deleted with the rest of the orphaned
+-- blocks.
+
+CREATE TABLE IF NOT EXISTS quarantined_events (
+    id                  BIGSERIAL PRIMARY KEY,
+    block_number        BIGINT NOT NULL,
+    tx_index            BIGINT NOT NULL,
+    log_index           BIGINT NOT NULL,
+    block_hash          BYTEA NOT NULL,
+    tx_hash             BYTEA NOT NULL,
+    address             BYTEA NOT NULL,
+    topics              BYTEA NOT NULL,
+    data                BYTEA NOT NULL,
+    timestamp           TIMESTAMPTZ NOT NULL,
+    tx_function         TEXT,
+    tx_from             BYTEA,
+    handler             TEXT NOT NULL,
+    aggregate           BYTEA,
+    error               TEXT NOT NULL,
+    blocked_by          BIGINT,
+    attempts            INTEGER NOT NULL DEFAULT 0,
+    quarantined_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_attempt_at     TIMESTAMPTZ,
+
+    CONSTRAINT uq_quarantined_events_log UNIQUE (block_number, tx_index, log_index)
+);
+
+COMMENT ON TABLE quarantined_events IS 'Logs whose handler failed, retried by `ghostnet-indexer quarantine retry`';
+COMMENT ON COLUMN quarantined_events.topics IS 'Topics concatenated, 32 bytes each';
+COMMENT ON COLUMN quarantined_events.handler IS 'Handler the log was routed to, e.g. position';
+COMMENT ON COLUMN quarantined_events.aggregate IS 'First indexed topic, the entity strict ordering is kept for';
+COMMENT ON COLUMN quarantined_events.error IS 'Error of the last attempt to handle the log';
+COMMENT ON COLUMN quarantined_events.blocked_by IS 'Earlier quarantined log of the same aggregate, for strict ordering';
+
+-- Chain of the schema, as for the tables of 20260217000001_chain_ids
+DO $$
+DECLARE
+    chain BIGINT := NULLIF(current_setting('ghostnet.chain_id', true), '')::BIGINT;
+BEGIN
+    IF chain IS NULL OR chain <= 0 THEN
+        RAISE EXCEPTION 'ghostnet.chain_id must be set to the chain of schema %', current_schema()
+            USING HINT = 'Run migrations through `ghostnet-indexer migrate`, or SET ghostnet.chain_id first';
+    END IF;
+    EXECUTE format(
+        'ALTER TABLE quarantined_events ADD COLUMN IF NOT EXISTS chain_id BIGINT NOT NULL DEFAULT %s',
+        chain
+    );
+    COMMENT ON COLUMN quarantined_events.chain_id IS 'Chain the row was indexed from';
+END $$;
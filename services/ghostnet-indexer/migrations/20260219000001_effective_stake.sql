@@ -0,0 +1,52 @@
+-- Effective stake: positions weighted by their yield multipliers
+--
+-- A position's effective stake is its stake weighted by the yield multiplier
+-- boosts active on it, summed: boosts of 2000 and 3000 bps make the stake
+-- count 1.5 times, rounded half up to whole wei. It equals `amount` without
+-- any, and when boosts are not tracked. The position handler reweights it on
+-- `BoostApplied` and `StakeAdded`, and the boost expirer once a multiplier
+-- lapses. Closed positions keep the effective stake they closed with.
+--
+-- Level stats gain the effective TVL of the active positions, maintained
+-- incrementally by the stats aggregator and rebuilt by
+-- `ghostnet-indexer recompute-stats`, which also reweights every active
+-- position from its boosts.
+
+ALTER TABLE positions
+    ADD COLUMN IF NOT EXISTS effective_stake NUMERIC(78, 0);
+
+COMMENT ON COLUMN positions.effective_stake IS 'Stake weighted by the active yield multipliers, in wei';
+
+-- Existing positions are weighted by the multipliers active now
+UPDATE positions p SET effective_stake = div(p.amount * (10000 + w.bps) + 5000, 10000)
+FROM (
+    SELECT
+        p.id,
+        COALESCE(SUM(GREATEST(b.value_bps, 0)) FILTER (
+            WHERE b.boost_type = 1 AND b.expired_at IS NULL AND b.expiry > NOW()
+              AND p.is_alive AND NOT p.is_extracted
+        ), 0) AS bps
+    FROM positions p
+    LEFT JOIN boosts b ON b.position_id = p.id
+    WHERE p.effective_stake IS NULL
+    GROUP BY p.id
+) w
+WHERE p.id = w.id;
+
+ALTER TABLE positions
+    ALTER COLUMN effective_stake SET NOT NULL;
+
+-- Effective stake leaderboard, ties broken by address as for the others
+CREATE INDEX IF NOT EXISTS idx_positions_effective_stake
+    ON positions(effective_stake DESC, user_address ASC)
+    WHERE is_alive = TRUE;
+
+ALTER TABLE level_stats
+    ADD COLUMN IF NOT EXISTS total_effective_staked NUMERIC(78, 0) NOT NULL DEFAULT 0;
+
+COMMENT ON COLUMN level_stats.total_effective_staked IS 'Sum of effective stakes across active positions';
+
+UPDATE level_stats ls SET total_effective_staked = COALESCE((
+    SELECT SUM(p.effective_stake) FROM positions p
+    WHERE p.level = ls.level AND p.is_alive AND NOT p.is_extracted
+), 0);
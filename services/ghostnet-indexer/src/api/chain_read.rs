@@ -0,0 +1,670 @@
+//! Contract reads for `GET /chain/read/:contract/:method`.
+//!
+//! Frontends need some live contract state the indexer does not
+//! materialize (allowances, pending rewards, a round's pools). The
+//! [`ChainReader`] calls those views for them, behind the API's rate
+//! limits, so they need no RPC endpoint of their own:
+//!
+//! 1. Only the views in [`VIEWS`] are called, each declared against the
+//!    `ghostnet-abi` binding of its contract, so no arbitrary calldata
+//!    reaches the node. Anything else is not found.
+//! 2. The `args` are parsed as the view's Solidity parameter types and
+//!    encoded with its selector.
+//! 3. Results are served from the [`MemoryCache`] by contract address and
+//!    calldata, for the TTL of the view.
+//! 4. Otherwise the view is called with `eth_call`, given
+//!    `api.chain_read_timeout_ms` regardless of the indexer's own RPC
+//!    timeouts, and the return data decoded with the binding into JSON.
+//!    A failed call answers 502 with the kind of failure.
+//!
+//! ```text
+//! GET /chain/read/data_token/allowance?args=0xowner,0xspender
+//!        │
+//!        ▼
+//!   ChainReader ──▶ VIEWS ──▶ MemoryCache ──▶ eth_call (timeout) ──▶ decode
+//!                   (404)       (hit)            (502)
+//! ```
+//!
+//! Amounts (`uint256`) are decimal strings, smaller integers and booleans
+//! are JSON numbers and booleans, and structs are objects with snake_case
+//! fields.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use alloy::dyn_abi::{DynSolType, DynSolValue};
+use alloy::primitives::{Address, Bytes, U256};
+use alloy::providers::{DynProvider, Provider};
+use alloy::rpc::types::TransactionRequest;
+use alloy::sol_types::SolCall;
+use alloy::transports::{RpcError, TransportErrorKind};
+use serde_json::{Value, json};
+use thiserror::Error;
+use tracing::{debug, warn};
+
+use crate::abi::{data_token, dead_pool, ghost_core};
+use crate::error::ApiError;
+use crate::indexer::{Contract, SharedRegistry};
+use crate::store::MemoryCache;
+
+/// TTL of views following user activity (balances, positions, rounds).
+const LIVE_TTL: Duration = Duration::from_secs(5);
+
+/// TTL of views of aggregate state.
+const AGGREGATE_TTL: Duration = Duration::from_secs(15);
+
+/// TTL of views of settings only governance changes.
+const SETTING_TTL: Duration = Duration::from_secs(300);
+
+/// Views `GET /chain/read` calls, by contract.
+pub const VIEWS: &[View] = &[
+    view::<ghost_core::getPositionCall>(Contract::GhostCore, LIVE_TTL),
+    view::<ghost_core::getPendingRewardsCall>(Contract::GhostCore, LIVE_TTL),
+    view::<ghost_core::getEffectiveDeathRateCall>(Contract::GhostCore, LIVE_TTL),
+    view::<ghost_core::getCullingRiskCall>(Contract::GhostCore, LIVE_TTL),
+    view::<ghost_core::isInLockPeriodCall>(Contract::GhostCore, LIVE_TTL),
+    view::<ghost_core::isAliveCall>(Contract::GhostCore, LIVE_TTL),
+    view::<ghost_core::getLevelStateCall>(Contract::GhostCore, AGGREGATE_TTL),
+    view::<ghost_core::getTotalValueLockedCall>(Contract::GhostCore, AGGREGATE_TTL),
+    view::<ghost_core::getSystemResetCall>(Contract::GhostCore, AGGREGATE_TTL),
+    view::<dead_pool::getRoundCall>(Contract::DeadPool, LIVE_TTL),
+    view::<data_token::balanceOfCall>(Contract::DataToken, LIVE_TTL),
+    view::<data_token::allowanceCall>(Contract::DataToken, LIVE_TTL),
+    view::<data_token::isExcludedFromTaxCall>(Contract::DataToken, SETTING_TTL),
+    view::<data_token::TAX_RATE_BPSCall>(Contract::DataToken, SETTING_TTL),
+];
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// VIEWS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// A contract view `GET /chain/read` may call.
+#[derive(Debug, Clone, Copy)]
+pub struct View {
+    /// Contract declaring the view.
+    pub contract: Contract,
+    /// How long a result is served from cache.
+    pub ttl: Duration,
+    /// Solidity signature, e.g. `balanceOf(address)`.
+    signature: &'static str,
+    /// Selector of the signature.
+    selector: [u8; 4],
+    /// Decode the return data into JSON.
+    decode: fn(&[u8]) -> alloy::sol_types::Result<Value>,
+}
+
+impl View {
+    /// Solidity name of the view, e.g. `balanceOf`.
+    #[must_use]
+    pub fn method(&self) -> &'static str {
+        self.signature
+            .split_once('(')
+            .map_or(self.signature, |(name, _)| name)
+    }
+
+    /// Solidity signature of the view, e.g. `balanceOf(address)`.
+    #[must_use]
+    pub const fn signature(&self) -> &'static str {
+        self.signature
+    }
+
+    /// Solidity types of the view's parameters, in order.
+    fn parameters(&self) -> impl Iterator<Item = &'static str> {
+        let signature = self.signature;
+        let start = signature.find('(').map_or(signature.len(), |at| at + 1);
+        let end = signature.rfind(')').unwrap_or(signature.len()).max(start);
+        signature[start..end].split(',').filter(|ty| !ty.is_empty())
+    }
+
+    /// Calldata calling the view with `args`, each parsed as its
+    /// parameter type.
+    fn calldata(&self, args: &[String]) -> Result<Bytes, ChainReadError> {
+        let types: Vec<_> = self.parameters().collect();
+        if args.len() != types.len() {
+            return Err(ChainReadError::Arguments(format!(
+                "{} takes {} arguments, got {}",
+                self.signature,
+                types.len(),
+                args.len()
+            )));
+        }
+
+        let mut values = Vec::with_capacity(types.len());
+        for (name, arg) in types.into_iter().zip(args) {
+            let value = DynSolType::parse(name)
+                .and_then(|ty| ty.coerce_str(arg))
+                .map_err(|e| ChainReadError::Arguments(format!("{arg:?} is not {name}: {e}")))?;
+            values.push(value);
+        }
+
+        let mut data = self.selector.to_vec();
+        data.extend(DynSolValue::Tuple(values).abi_encode_params());
+        Ok(data.into())
+    }
+}
+
+/// Declare the view `C` of `contract`.
+const fn view<C: JsonView>(contract: Contract, ttl: Duration) -> View {
+    View {
+        contract,
+        ttl,
+        signature: C::SIGNATURE,
+        selector: C::SELECTOR,
+        decode: decode::<C>,
+    }
+}
+
+/// Decode the return data of `C` into JSON.
+fn decode<C: JsonView>(data: &[u8]) -> alloy::sol_types::Result<Value> {
+    C::abi_decode_returns(data).map(C::to_json)
+}
+
+/// Find the view `method` of `contract`.
+fn find_view(contract: &str, method: &str) -> Result<&'static View, ChainReadError> {
+    let contract = contract
+        .parse::<Contract>()
+        .map_err(|_| ChainReadError::UnknownContract(contract.to_string()))?;
+    VIEWS
+        .iter()
+        .find(|view| view.contract == contract && view.method() == method)
+        .ok_or_else(|| ChainReadError::UnknownMethod {
+            contract,
+            method: method.to_string(),
+        })
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// JSON RESULTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// A view call whose return value is served as JSON.
+trait JsonView: SolCall {
+    /// The decoded return value as JSON.
+    fn to_json(returns: Self::Return) -> Value;
+}
+
+/// A `uint256` as a decimal string, which JSON numbers cannot hold.
+fn amount(value: U256) -> Value {
+    Value::String(value.to_string())
+}
+
+impl JsonView for ghost_core::getPositionCall {
+    fn to_json(position: ghost_core::Position) -> Value {
+        json!({
+            "amount": amount(position.amount),
+            "level": position.level,
+            "entry_timestamp": position.entryTimestamp,
+            "last_add_timestamp": position.lastAddTimestamp,
+            "reward_debt": amount(position.rewardDebt),
+            "alive": position.alive,
+            "ghost_streak": position.ghostStreak,
+        })
+    }
+}
+
+impl JsonView for ghost_core::getPendingRewardsCall {
+    fn to_json(rewards: U256) -> Value {
+        amount(rewards)
+    }
+}
+
+impl JsonView for ghost_core::getEffectiveDeathRateCall {
+    fn to_json(rate_bps: u16) -> Value {
+        json!(rate_bps)
+    }
+}
+
+impl JsonView for ghost_core::getCullingRiskCall {
+    fn to_json(risk: ghost_core::getCullingRiskReturn) -> Value {
+        json!({
+            "risk_bps": risk.riskBps,
+            "is_eligible": risk.isEligible,
+            "capacity_pct": risk.capacityPct,
+        })
+    }
+}
+
+impl JsonView for ghost_core::isInLockPeriodCall {
+    fn to_json(locked: bool) -> Value {
+        json!(locked)
+    }
+}
+
+impl JsonView for ghost_core::isAliveCall {
+    fn to_json(alive: bool) -> Value {
+        json!(alive)
+    }
+}
+
+impl JsonView for ghost_core::getLevelStateCall {
+    fn to_json(state: ghost_core::LevelState) -> Value {
+        json!({
+            "total_staked": amount(state.totalStaked),
+            "alive_count": amount(state.aliveCount),
+            "acc_rewards_per_share": amount(state.accRewardsPerShare),
+            "next_scan_time": state.nextScanTime,
+        })
+    }
+}
+
+impl JsonView for ghost_core::getTotalValueLockedCall {
+    fn to_json(total: U256) -> Value {
+        amount(total)
+    }
+}
+
+impl JsonView for ghost_core::getSystemResetCall {
+    fn to_json(reset: ghost_core::SystemReset) -> Value {
+        json!({
+            "deadline": reset.deadline,
+            "last_depositor": reset.lastDepositor,
+            "last_deposit_time": reset.lastDepositTime,
+            "epoch": amount(reset.epoch),
+            "penalty_bps": reset.penaltyBps,
+        })
+    }
+}
+
+impl JsonView for dead_pool::getRoundCall {
+    fn to_json(round: dead_pool::Round) -> Value {
+        json!({
+            "round_type": round.roundType,
+            "target_level": round.targetLevel,
+            "line": amount(round.line),
+            "over_pool": amount(round.overPool),
+            "under_pool": amount(round.underPool),
+            "deadline": round.deadline,
+            "resolve_time": round.resolveTime,
+            "resolved": round.resolved,
+            "outcome": round.outcome,
+        })
+    }
+}
+
+impl JsonView for data_token::balanceOfCall {
+    fn to_json(balance: U256) -> Value {
+        amount(balance)
+    }
+}
+
+impl JsonView for data_token::allowanceCall {
+    fn to_json(allowance: U256) -> Value {
+        amount(allowance)
+    }
+}
+
+impl JsonView for data_token::isExcludedFromTaxCall {
+    fn to_json(excluded: bool) -> Value {
+        json!(excluded)
+    }
+}
+
+impl JsonView for data_token::TAX_RATE_BPSCall {
+    fn to_json(rate_bps: u16) -> Value {
+        json!(rate_bps)
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// ERRORS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Why a contract read failed.
+#[derive(Debug, Error)]
+pub enum ChainReadError {
+    /// The contract is not known, or not enabled.
+    #[error("unknown contract {0:?}")]
+    UnknownContract(String),
+
+    /// The contract has no view of that name in [`VIEWS`].
+    #[error("{contract} has no readable view {method:?}")]
+    UnknownMethod {
+        /// The contract.
+        contract: Contract,
+        /// Method requested.
+        method: String,
+    },
+
+    /// The arguments do not match the view's parameters.
+    #[error("invalid arguments: {0}")]
+    Arguments(String),
+
+    /// The call did not return within `api.chain_read_timeout_ms`.
+    #[error("contract read timed out")]
+    Timeout,
+
+    /// The node failed the call.
+    #[error("contract read failed ({class})")]
+    Rpc {
+        /// Kind of failure, e.g. `reverted` or `transport`.
+        class: &'static str,
+        /// The error.
+        #[source]
+        source: RpcError<TransportErrorKind>,
+    },
+
+    /// The return data does not decode as the view's return type.
+    #[error("undecodable return data")]
+    Decode(#[source] alloy::sol_types::Error),
+}
+
+impl From<ChainReadError> for ApiError {
+    fn from(err: ChainReadError) -> Self {
+        match err {
+            ChainReadError::UnknownContract(_) | ChainReadError::UnknownMethod { .. } => {
+                Self::NotFound(err.to_string())
+            }
+            ChainReadError::Arguments(_) => Self::BadRequest(err.to_string()),
+            ChainReadError::Timeout => Self::BadGateway { class: "timeout" },
+            ChainReadError::Rpc { class, .. } => Self::BadGateway { class },
+            ChainReadError::Decode(_) => Self::BadGateway { class: "decode" },
+        }
+    }
+}
+
+/// Kind of failure of an `eth_call`, reported to clients.
+fn classify(error: &RpcError<TransportErrorKind>) -> &'static str {
+    match error {
+        RpcError::ErrorResp(payload) if payload.code == 3 || payload.message.contains("revert") => {
+            "reverted"
+        }
+        RpcError::ErrorResp(_) => "rpc_error",
+        RpcError::Transport(_) => "transport",
+        RpcError::NullResp | RpcError::DeserError { .. } => "malformed_response",
+        _ => "rpc",
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// CHAIN READER
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Calls the views in [`VIEWS`] for `GET /chain/read`, caching the results.
+pub struct ChainReader {
+    provider: DynProvider,
+    /// Addresses of the enabled contracts, following reloads.
+    registry: SharedRegistry,
+    cache: Arc<MemoryCache>,
+    /// Budget of one `eth_call`.
+    timeout: Duration,
+}
+
+impl std::fmt::Debug for ChainReader {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChainReader")
+            .field("timeout", &self.timeout)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ChainReader {
+    /// Create a reader calling the contracts of `registry` through
+    /// `provider`, giving each call `timeout`
+    /// (`api.chain_read_timeout_ms`).
+    #[must_use]
+    pub fn new<P>(
+        provider: P,
+        registry: SharedRegistry,
+        cache: Arc<MemoryCache>,
+        timeout: Duration,
+    ) -> Self
+    where
+        P: Provider + 'static,
+    {
+        Self {
+            provider: provider.erased(),
+            registry,
+            cache,
+            timeout,
+        }
+    }
+
+    /// Call the view `method` of `contract` with `args`, and decode its
+    /// result.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the view is not in [`VIEWS`] or its contract is
+    /// disabled, the arguments do not match its parameters, or the call
+    /// fails, times out or returns undecodable data.
+    pub async fn read(
+        &self,
+        contract: &str,
+        method: &str,
+        args: &[String],
+    ) -> Result<Value, ChainReadError> {
+        let view = find_view(contract, method)?;
+        let address = self
+            .registry
+            .current()
+            .address(view.contract)
+            .ok_or_else(|| ChainReadError::UnknownContract(contract.to_string()))?;
+        let calldata = view.calldata(args)?;
+        if let Some(value) = self.cache.get_chain_read(address, &calldata) {
+            return Ok(value);
+        }
+
+        let value = self.call(view, address, calldata.clone()).await?;
+        debug!(contract, method, "Read contract");
+        self.cache
+            .set_chain_read(address, calldata, value.clone(), view.ttl);
+        Ok(value)
+    }
+
+    /// Call `view` at `address` within the budget.
+    async fn call(
+        &self,
+        view: &View,
+        address: Address,
+        calldata: Bytes,
+    ) -> Result<Value, ChainReadError> {
+        let request = TransactionRequest::default()
+            .to(address)
+            .input(calldata.into());
+        let output = match tokio::time::timeout(self.timeout, self.provider.call(request)).await {
+            Ok(Ok(output)) => output,
+            Ok(Err(e)) => {
+                let class = classify(&e);
+                warn!(signature = view.signature, class, error = %e, "Contract read failed");
+                return Err(ChainReadError::Rpc { class, source: e });
+            }
+            Err(_) => {
+                warn!(
+                    signature = view.signature,
+                    timeout = ?self.timeout,
+                    "Contract read timed out"
+                );
+                return Err(ChainReadError::Timeout);
+            }
+        };
+
+        (view.decode)(&output).map_err(|e| {
+            warn!(signature = view.signature, error = %e, "Undecodable contract read");
+            ChainReadError::Decode(e)
+        })
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::collections::HashMap;
+
+    use alloy::providers::ProviderBuilder;
+    use alloy::transports::mock::Asserter;
+    use axum::http::StatusCode;
+    use axum::response::IntoResponse;
+
+    use super::*;
+    use crate::config::ContractAddresses;
+    use crate::indexer::ContractRegistry;
+
+    const OWNER: &str = "0x00000000000000000000000000000000000000aa";
+    const SPENDER: &str = "0x00000000000000000000000000000000000000bb";
+
+    fn addresses() -> ContractAddresses {
+        ContractAddresses {
+            ghost_core: "0x0000000000000000000000000000000000000001".into(),
+            trace_scan: "0x0000000000000000000000000000000000000002".into(),
+            dead_pool: "0x0000000000000000000000000000000000000003".into(),
+            data_token: "0x0000000000000000000000000000000000000004".into(),
+            fee_router: "0x0000000000000000000000000000000000000005".into(),
+            rewards_distributor: "0x0000000000000000000000000000000000000006".into(),
+            disabled: vec!["dead_pool".into()],
+            code_hashes: HashMap::new(),
+            additional: HashMap::new(),
+        }
+    }
+
+    fn reader(asserter: Asserter) -> ChainReader {
+        let provider = ProviderBuilder::new().connect_mocked_client(asserter);
+        let registry = ContractRegistry::from_config(&addresses()).unwrap();
+        ChainReader::new(
+            provider,
+            SharedRegistry::new(Arc::new(registry)),
+            Arc::new(MemoryCache::new()),
+            Duration::from_secs(1),
+        )
+    }
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(ToString::to_string).collect()
+    }
+
+    fn status(err: ChainReadError) -> StatusCode {
+        ApiError::from(err).into_response().status()
+    }
+
+    async fn refused(
+        reader: &ChainReader,
+        contract: &str,
+        method: &str,
+        args: &[&str],
+    ) -> ChainReadError {
+        let args: Vec<String> = args.iter().map(ToString::to_string).collect();
+        reader.read(contract, method, &args).await.unwrap_err()
+    }
+
+    #[tokio::test]
+    async fn only_allowlisted_views_are_called() {
+        // No response is queued: none of these reach the provider
+        let reader = reader(Asserter::new());
+
+        let unknown = refused(&reader, "vault", "balanceOf", &[OWNER]).await;
+        assert!(matches!(unknown, ChainReadError::UnknownContract(_)));
+        assert_eq!(status(unknown), StatusCode::NOT_FOUND);
+
+        // State-changing functions and views of other contracts are not listed
+        let transfer = refused(&reader, "data_token", "transfer", &[OWNER, "1"]).await;
+        assert!(matches!(transfer, ChainReadError::UnknownMethod { .. }));
+        assert_eq!(status(transfer), StatusCode::NOT_FOUND);
+        let foreign = refused(&reader, "fee_router", "balanceOf", &[OWNER]).await;
+        assert!(matches!(foreign, ChainReadError::UnknownMethod { .. }));
+
+        // Disabled contracts have no address to call
+        let disabled = refused(&reader, "dead_pool", "getRound", &["7"]).await;
+        assert!(matches!(disabled, ChainReadError::UnknownContract(_)));
+
+        let missing = refused(&reader, "data_token", "allowance", &[OWNER]).await;
+        assert!(matches!(missing, ChainReadError::Arguments(_)));
+        assert_eq!(status(missing), StatusCode::BAD_REQUEST);
+        let malformed = refused(&reader, "data_token", "balanceOf", &["0x12"]).await;
+        assert!(matches!(malformed, ChainReadError::Arguments(_)));
+    }
+
+    #[tokio::test]
+    async fn reads_are_cached_for_the_view_ttl() {
+        let asserter = Asserter::new();
+        let allowance = U256::from(10).pow(U256::from(24));
+        asserter.push_success(&Bytes::from(data_token::allowanceCall::abi_encode_returns(
+            &allowance,
+        )));
+        let reader = reader(asserter);
+        let owner_spender = args(&[OWNER, SPENDER]);
+
+        let first = reader
+            .read("data_token", "allowance", &owner_spender)
+            .await
+            .unwrap();
+        assert_eq!(first, json!("1000000000000000000000000"));
+
+        // Served from cache: the provider has nothing more queued
+        let second = reader
+            .read("data_token", "allowance", &owner_spender)
+            .await
+            .unwrap();
+        assert_eq!(second, first);
+
+        // Other arguments are another read
+        let reversed = reader
+            .read("data_token", "allowance", &args(&[SPENDER, OWNER]))
+            .await;
+        assert!(reversed.is_err());
+    }
+
+    #[tokio::test]
+    async fn struct_views_decode_to_objects() {
+        let asserter = Asserter::new();
+        let position = ghost_core::getPositionCall::abi_encode_returns(&ghost_core::Position {
+            amount: U256::from(1_500_000_000_000_000_000_u64),
+            level: 3,
+            entryTimestamp: 1_700_000_000,
+            lastAddTimestamp: 1_700_000_600,
+            rewardDebt: U256::from(42),
+            alive: true,
+            ghostStreak: 4,
+        });
+        asserter.push_success(&Bytes::from(position));
+
+        let position = reader(asserter)
+            .read("ghost_core", "getPosition", &args(&[OWNER]))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            position,
+            json!({
+                "amount": "1500000000000000000",
+                "level": 3,
+                "entry_timestamp": 1_700_000_000,
+                "last_add_timestamp": 1_700_000_600,
+                "reward_debt": "42",
+                "alive": true,
+                "ghost_streak": 4,
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn failed_calls_report_their_class() {
+        let asserter = Asserter::new();
+        asserter.push_failure_msg("execution reverted");
+        asserter.push_success(&Bytes::from_static(&[0x01]));
+        let reader = reader(asserter);
+
+        let reverted = reader
+            .read("ghost_core", "getTotalValueLocked", &[])
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            reverted,
+            ChainReadError::Rpc {
+                class: "reverted",
+                ..
+            }
+        ));
+        assert_eq!(status(reverted), StatusCode::BAD_GATEWAY);
+
+        // Failures are not cached; a short return is undecodable
+        let truncated = reader
+            .read("ghost_core", "getTotalValueLocked", &[])
+            .await
+            .unwrap_err();
+        assert!(matches!(truncated, ChainReadError::Decode(_)));
+        assert_eq!(status(truncated), StatusCode::BAD_GATEWAY);
+    }
+}
@@ -0,0 +1,300 @@
+//! REST API for indexed GHOSTNET data.
+//!
+//! The API is read-only and serves data that is already materialized by the
+//! indexer (cached leaderboards, aggregate stats, scan records, position
+//! history). Route handlers never run expensive aggregations per request;
+//! windowed token stats sum at most one pre-aggregated row per hour,
+//! survival stats group only exited positions, through a partial index, and
+//! level history buckets one row per scan over a bounded range.
+//!
+//! # Endpoints
+//!
+//! All routes are nested under `/api/v1`, apart from `GET /health`, which
+//! answers 503 while the database is unreachable or a read-only standby
+//! (given a [health store](ApiState::with_health_store)), or while the event
+//! publisher has too many messages buffered or dead-lettered (given its
+//! [health](ApiState::with_publisher_health)).
+//!
+//! With several chains indexed, [`chains_router`] serves the routes of each
+//! chain, from that chain's state, under `/api/v1/chains/:chain_id`, and
+//! those of the primary chain under `/api/v1` as before. Its `GET /health`
+//! reports the database and publisher of every chain.
+//!
+//! | Method | Path | Description |
+//! |--------|------|-------------|
+//! | `GET` | `/chain/read/:contract/:method?args=` | Return value of an allowlisted contract view (see [`chain_read`](self::chain_read)) |
+//! | `GET` | `/leaderboard/:type?limit=` | Cached leaderboard ([`LeaderboardType`](crate::types::enums::LeaderboardType)) |
+//! | `GET` | `/positions?level=&min_stake=&max_stake=&min_streak=&active=&created_after=&limit=&cursor=` | Positions of all users, newest first |
+//! | `GET` | `/positions/:address` | Active position of an address with its active boosts |
+//! | `GET` | `/positions/:address/cascades?limit=` | Cascade earnings of an address, total and per scan |
+//! | `GET` | `/positions/:address/history?limit=&before=` | Position history of an address, newest first |
+//! | `GET` | `/rounds/:id/odds-history` | Implied odds and payout multiples of a `DeadPool` round after each of its bets |
+//! | `GET` | `/scans/next` | Predicted next scan per level, with the data it was derived from |
+//! | `GET` | `/scans/:id` | Scan lifecycle with linked deaths and finalization latency |
+//! | `GET` | `/stats/levels/:level/history?from=&to=&bucket=` | Death rate, survivors and TVL of a level per `5m`, `1h` or `1d` bucket |
+//! | `GET` | `/stats/survival` | Survival time, cull rate and ghost streaks at exit per level |
+//! | `GET` | `/stats/token?window_secs=&address=` | Burn rate, tax totals and optional per-address flows |
+//! | `GET` | `/token/holders?sort=&limit=` | DATA holders by balance, first seen or last activity |
+//! | `GET` | `/token/holders/:address` | DATA balance of an address |
+//! | `GET` | `/users/:address` | Position, history, bets, cascade earnings and token flows of an address in one document |
+//! | `GET` | `/ws` | WebSocket stream of published events, resumable by sequence (see [`ClientMessage`]) |
+//!
+//! Requests are rate limited per client IP by an optional [`RateLimiter`]
+//! (see [`rate_limit`](self::rate_limit)).
+//!
+//! # Usage
+//!
+//! ```ignore
+//! use ghostnet_indexer::api::{self, ApiState, ChainReader, RateLimiter};
+//! use ghostnet_indexer::indexer::{LeaderboardRefresher, ScanPredictor, UserProfileService};
+//!
+//! let leaderboards = Arc::new(LeaderboardRefresher::new(store, cache, &settings.leaderboard));
+//! leaderboards.spawn_refresh_task(shutdown.clone());
+//!
+//! // Shared with the ScanHandler, which invalidates predictions on ScanExecuted
+//! let predictor = Arc::new(ScanPredictor::new(store.clone(), &settings.scan_prediction));
+//!
+//! let limiter = Arc::new(RateLimiter::new(cache, &settings.api.rate_limit));
+//! limiter.spawn_cleanup_task(shutdown.clone());
+//! limiter.follow(reloader.subscribe(), shutdown.clone());
+//!
+//! let profiles = Arc::new(UserProfileService::new(store.clone(), &settings.user_profiles));
+//!
+//! let timeout = settings.api.chain_read_timeout();
+//! let reader = Arc::new(ChainReader::new(provider, registry.clone(), cache.clone(), timeout));
+//!
+//! let state = ApiState::new(store, leaderboards, &settings.leaderboard, &settings.token_flows)
+//!     .with_scan_predictor(predictor)
+//!     .with_user_profiles(profiles)
+//!     .with_rate_limiter(limiter)
+//!     .with_positions_cache(cache)
+//!     .with_health_store(store.as_ref().clone())
+//!     .with_publisher_health(PublisherHealth::new(iggy, &settings.iggy))
+//!     .with_event_log(event_log)
+//!     .with_wire_schemas(schemas)
+//!     .with_boosts(store.clone())
+//!     .with_chain_reader(reader);
+//! api::serve(&settings.api, api::router(state), shutdown).await?;
+//! ```
+
+pub mod chain_read;
+pub mod rate_limit;
+mod routes;
+mod server;
+
+use std::sync::Arc;
+
+use std::time::Duration;
+
+use crate::config::{LeaderboardSettings, TokenFlowSettings};
+use crate::indexer::{LeaderboardRefresher, ScanPredictor, UserProfileService};
+use crate::ports::BoostStore;
+use crate::store::{MemoryCache, PostgresStore};
+use crate::streaming::{EventLog, PublisherHealth, WireSchemas};
+
+pub use chain_read::{ChainReadError, ChainReader, VIEWS, View};
+pub use rate_limit::{HEALTH_PATH, RateLimiter};
+pub use routes::chain::{ChainReadQuery, ChainReadResponse};
+pub use routes::events::{ClientMessage, ServerMessage};
+pub use routes::holders::{
+    DEFAULT_HOLDERS_LIMIT, HolderBody, HoldersQuery, HoldersResponse, MAX_HOLDERS_LIMIT,
+};
+pub use routes::leaderboards::{LeaderboardQuery, LeaderboardResponse};
+pub use routes::positions::{
+    ActiveBoost, CascadeEarningsQuery, DEFAULT_HISTORY_LIMIT, DEFAULT_POSITIONS_LIMIT,
+    MAX_HISTORY_LIMIT, MAX_POSITIONS_LIMIT, PositionHistoryQuery, PositionHistoryResponse,
+    PositionResponse, PositionsQuery, PositionsResponse,
+};
+pub use routes::rounds::OddsHistoryResponse;
+pub use routes::scans::{NextScan, NextScansResponse, ScanResponse};
+pub use routes::stats::{
+    AddressFlowsBody, SurvivalStatsResponse, TokenStatsQuery, TokenStatsResponse,
+};
+pub use server::{chains_router, router, serve};
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// API STATE
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Shared state for API route handlers.
+pub struct ApiState<S> {
+    /// Store for queries against pre-aggregated tables.
+    store: Arc<S>,
+    /// Cached leaderboards.
+    leaderboards: Arc<LeaderboardRefresher<S>>,
+    /// Leaderboard entries returned when a request has no `limit`.
+    leaderboard_default_limit: u32,
+    /// Token stats window used when a request has no `window_secs`.
+    token_flow_window: Duration,
+    /// Next-scan predictions; `GET /scans/next` is not found without one.
+    scan_predictor: Option<Arc<ScanPredictor<S>>>,
+    /// Profile documents; `GET /users/:address` is not found without one.
+    user_profiles: Option<Arc<UserProfileService<S>>>,
+    /// Per-client rate limits; requests are not limited without one.
+    rate_limiter: Option<Arc<RateLimiter>>,
+    /// Cache for the unfiltered first page of `GET /positions`; every
+    /// listing goes to the store without one.
+    positions_cache: Option<Arc<MemoryCache>>,
+    /// Database checked by `GET /health`, which only reports the server as
+    /// up without one.
+    health_store: Option<PostgresStore>,
+    /// Delivery counters of the event publisher reported by `GET /health`,
+    /// which leaves them out without one.
+    publisher_health: Option<PublisherHealth>,
+    /// Published events streamed over `GET /ws`, which is not found
+    /// without one.
+    event_log: Option<Arc<EventLog>>,
+    /// Schema versions `GET /ws` clients can subscribe to; every version
+    /// is offered without one.
+    wire_schemas: Option<Arc<WireSchemas>>,
+    /// Boosts listed by `GET /positions/:address`, which lists none without
+    /// one.
+    boosts: Option<Arc<dyn BoostStore>>,
+    /// Contract views called by `GET /chain/read`, which is not found
+    /// without one.
+    chain_reader: Option<Arc<ChainReader>>,
+}
+
+impl<S> ApiState<S> {
+    /// Create the API state.
+    #[must_use]
+    pub const fn new(
+        store: Arc<S>,
+        leaderboards: Arc<LeaderboardRefresher<S>>,
+        leaderboard: &LeaderboardSettings,
+        token_flows: &TokenFlowSettings,
+    ) -> Self {
+        Self {
+            store,
+            leaderboards,
+            leaderboard_default_limit: leaderboard.default_limit,
+            token_flow_window: token_flows.default_window(),
+            scan_predictor: None,
+            user_profiles: None,
+            rate_limiter: None,
+            positions_cache: None,
+            health_store: None,
+            publisher_health: None,
+            event_log: None,
+            wire_schemas: None,
+            boosts: None,
+            chain_reader: None,
+        }
+    }
+
+    /// Serve next-scan predictions from `predictor`.
+    #[must_use]
+    pub fn with_scan_predictor(mut self, predictor: Arc<ScanPredictor<S>>) -> Self {
+        self.scan_predictor = Some(predictor);
+        self
+    }
+
+    /// Serve user profile documents from `profiles`.
+    #[must_use]
+    pub fn with_user_profiles(mut self, profiles: Arc<UserProfileService<S>>) -> Self {
+        self.user_profiles = Some(profiles);
+        self
+    }
+
+    /// Rate limit requests with `limiter`.
+    #[must_use]
+    pub fn with_rate_limiter(mut self, limiter: Arc<RateLimiter>) -> Self {
+        self.rate_limiter = Some(limiter);
+        self
+    }
+
+    /// Serve the unfiltered first page of `GET /positions` from `cache`.
+    #[must_use]
+    pub fn with_positions_cache(mut self, cache: Arc<MemoryCache>) -> Self {
+        self.positions_cache = Some(cache);
+        self
+    }
+
+    /// Report the connectivity and replication state of `store` on
+    /// `GET /health`.
+    #[must_use]
+    pub fn with_health_store(mut self, store: PostgresStore) -> Self {
+        self.health_store = Some(store);
+        self
+    }
+
+    /// Report the delivery counters of the event publisher on
+    /// `GET /health`, degraded past its thresholds.
+    #[must_use]
+    pub fn with_publisher_health(mut self, health: PublisherHealth) -> Self {
+        self.publisher_health = Some(health);
+        self
+    }
+
+    /// Stream the events published through `log` over `GET /ws`.
+    #[must_use]
+    pub fn with_event_log(mut self, log: Arc<EventLog>) -> Self {
+        self.event_log = Some(log);
+        self
+    }
+
+    /// Offer the schema versions published by `schemas` on `GET /ws`.
+    #[must_use]
+    pub fn with_wire_schemas(mut self, schemas: Arc<WireSchemas>) -> Self {
+        self.wire_schemas = Some(schemas);
+        self
+    }
+
+    /// List the active boosts from `boosts` on `GET /positions/:address`.
+    #[must_use]
+    pub fn with_boosts(mut self, boosts: Arc<dyn BoostStore>) -> Self {
+        self.boosts = Some(boosts);
+        self
+    }
+
+    /// Call contract views with `reader` on `GET /chain/read`.
+    #[must_use]
+    pub fn with_chain_reader(mut self, reader: Arc<ChainReader>) -> Self {
+        self.chain_reader = Some(reader);
+        self
+    }
+}
+
+// Manual impl: the boost store is a trait object without `Debug`.
+impl<S: std::fmt::Debug> std::fmt::Debug for ApiState<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ApiState")
+            .field("store", &self.store)
+            .field("leaderboards", &self.leaderboards)
+            .field("leaderboard_default_limit", &self.leaderboard_default_limit)
+            .field("token_flow_window", &self.token_flow_window)
+            .field("scan_predictor", &self.scan_predictor)
+            .field("user_profiles", &self.user_profiles)
+            .field("rate_limiter", &self.rate_limiter)
+            .field("positions_cache", &self.positions_cache)
+            .field("health_store", &self.health_store)
+            .field("publisher_health", &self.publisher_health)
+            .field("event_log", &self.event_log)
+            .field("wire_schemas", &self.wire_schemas)
+            .field("boosts", &self.boosts.is_some())
+            .field("chain_reader", &self.chain_reader)
+            .finish()
+    }
+}
+
+// Manual impl: `S` itself is shared behind `Arc` and need not be `Clone`.
+impl<S> Clone for ApiState<S> {
+    fn clone(&self) -> Self {
+        Self {
+            store: Arc::clone(&self.store),
+            leaderboards: Arc::clone(&self.leaderboards),
+            leaderboard_default_limit: self.leaderboard_default_limit,
+            token_flow_window: self.token_flow_window,
+            scan_predictor: self.scan_predictor.clone(),
+            user_profiles: self.user_profiles.clone(),
+            rate_limiter: self.rate_limiter.clone(),
+            positions_cache: self.positions_cache.clone(),
+            health_store: self.health_store.clone(),
+            publisher_health: self.publisher_health.clone(),
+            event_log: self.event_log.clone(),
+            wire_schemas: self.wire_schemas.clone(),
+            boosts: self.boosts.clone(),
+            chain_reader: self.chain_reader.clone(),
+        }
+    }
+}
@@ -0,0 +1,398 @@
+//! Per-client rate limiting.
+//!
+//! [`RateLimiter`] counts requests in the rate limit windows of the
+//! [`MemoryCache`], keyed `ip:{addr}`. A client may make `burst_size`
+//! requests within a second and `requests_per_minute` within a minute; past
+//! either, requests are answered with `429 Too Many Requests`, a
+//! `Retry-After` header and the JSON error body of
+//! [`ApiError::RateLimited`].
+//!
+//! WebSocket connection attempts are limited separately per client, and
+//! subscribe messages per connection.
+//!
+//! # Client IP
+//!
+//! The client is the peer address of the connection, unless
+//! `trusted_proxy_header` names a header set by a reverse proxy in front of
+//! the indexer. Forwarding headers are ignored otherwise, because any client
+//! can send them. Loopback clients and the health endpoint are exempt.
+
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::extract::{ConnectInfo, Request, State};
+use axum::http::HeaderMap;
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use parking_lot::RwLock;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+use tokio_util::sync::CancellationToken;
+use tracing::debug;
+
+use crate::config::{RateLimitSettings, Settings};
+use crate::error::ApiError;
+use crate::ports::Cache;
+use crate::store::MemoryCache;
+
+/// Path of the health endpoint, which is never rate limited.
+pub const HEALTH_PATH: &str = "/health";
+
+/// Window of the burst limit.
+const BURST_WINDOW_SECS: u64 = 1;
+
+/// Window of the per-minute limits.
+const MINUTE_WINDOW_SECS: u64 = 60;
+
+/// How often expired windows are removed from the cache.
+const CLEANUP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Age after which a window is expired, for every window size.
+const MAX_WINDOW_AGE_SECS: u64 = 2 * MINUTE_WINDOW_SECS;
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// RATE LIMITER
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Per-client rate limits for the API.
+#[derive(Debug)]
+pub struct RateLimiter {
+    /// Cache holding the windows.
+    cache: Arc<MemoryCache>,
+    /// Limits in effect; replaced on config reload.
+    settings: RwLock<RateLimitSettings>,
+}
+
+impl RateLimiter {
+    /// Create a rate limiter counting in `cache`.
+    #[must_use]
+    pub fn new(cache: Arc<MemoryCache>, settings: &RateLimitSettings) -> Self {
+        Self {
+            cache,
+            settings: RwLock::new(settings.clone()),
+        }
+    }
+
+    /// Replace the limits in effect.
+    pub fn apply(&self, settings: &RateLimitSettings) {
+        *self.settings.write() = settings.clone();
+    }
+
+    /// Resolve the client IP of a request from its headers and peer address.
+    ///
+    /// With a trusted proxy header, its last (rightmost) address is the
+    /// client, as that is the one appended by the proxy. Without the header,
+    /// or if it holds no address, the peer address is the client.
+    #[must_use]
+    pub fn client_ip(&self, headers: &HeaderMap, peer: Option<IpAddr>) -> Option<IpAddr> {
+        let header = self.settings.read().trusted_proxy_header.clone();
+        let forwarded = header
+            .and_then(|name| headers.get(name.as_str()))
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.rsplit(',').find_map(parse_ip));
+        forwarded.or(peer)
+    }
+
+    /// Count a request of `client` against its limits.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ApiError::RateLimited`] if the client is over a limit.
+    pub fn check_request(&self, client: Option<IpAddr>) -> Result<(), ApiError> {
+        if client.is_some_and(|ip| ip.is_loopback()) {
+            return Ok(());
+        }
+        let key = client_key("ip", client);
+        let (burst, per_minute) = {
+            let settings = self.settings.read();
+            (settings.burst_size, settings.requests_per_minute)
+        };
+        self.check(&key, burst, BURST_WINDOW_SECS)?;
+        self.check(&format!("{key}:minute"), per_minute, MINUTE_WINDOW_SECS)
+    }
+
+    /// Count a WebSocket connection attempt of `client`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ApiError::RateLimited`] if the client is over the limit.
+    pub fn check_ws_connect(&self, client: Option<IpAddr>) -> Result<(), ApiError> {
+        if client.is_some_and(|ip| ip.is_loopback()) {
+            return Ok(());
+        }
+        let limit = self.settings.read().websocket_connects_per_minute;
+        self.check(&client_key("ws:ip", client), limit, MINUTE_WINDOW_SECS)
+    }
+
+    /// Count a subscribe message on a WebSocket connection.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ApiError::RateLimited`] if the connection is over the limit.
+    pub fn check_subscribe(&self, connection_id: u64) -> Result<(), ApiError> {
+        let limit = self.settings.read().subscribe_messages_per_minute;
+        self.check(
+            &format!("ws:conn:{connection_id}"),
+            limit,
+            MINUTE_WINDOW_SECS,
+        )
+    }
+
+    /// Count an attempt under `key`.
+    fn check(&self, key: &str, limit: u32, window_secs: u64) -> Result<(), ApiError> {
+        if self.cache.check_rate_limit(key, limit, window_secs) {
+            return Ok(());
+        }
+        debug!(key, limit, window_secs, "Rate limited");
+        Err(ApiError::RateLimited {
+            retry_after_secs: window_secs - current_timestamp() % window_secs,
+        })
+    }
+
+    /// Spawn the task removing expired windows from the cache every minute,
+    /// until `shutdown` is cancelled.
+    pub fn spawn_cleanup_task(self: &Arc<Self>, shutdown: CancellationToken) -> JoinHandle<()> {
+        let limiter = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(CLEANUP_INTERVAL);
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+            loop {
+                tokio::select! {
+                    () = shutdown.cancelled() => break,
+                    _ = ticker.tick() => {}
+                }
+                limiter.cache.cleanup_rate_limits(MAX_WINDOW_AGE_SECS);
+            }
+        })
+    }
+
+    /// Spawn the task applying reloaded limits from `settings`, e.g.
+    /// [`ConfigReloader::subscribe`](crate::config::ConfigReloader::subscribe),
+    /// until `shutdown` is cancelled.
+    pub fn follow(
+        self: &Arc<Self>,
+        mut settings: watch::Receiver<Settings>,
+        shutdown: CancellationToken,
+    ) -> JoinHandle<()> {
+        let limiter = Arc::clone(self);
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    () = shutdown.cancelled() => break,
+                    changed = settings.changed() => {
+                        if changed.is_err() {
+                            break;
+                        }
+                    }
+                }
+                let reloaded = settings.borrow_and_update().api.rate_limit.clone();
+                limiter.apply(&reloaded);
+            }
+        })
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// MIDDLEWARE
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Rate limit a request by its client IP.
+///
+/// The peer address comes from [`ConnectInfo`], see [`serve`](super::serve).
+pub async fn limit_requests(
+    State(limiter): State<Arc<RateLimiter>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if request.uri().path() == HEALTH_PATH {
+        return next.run(request).await;
+    }
+
+    let peer = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip());
+    let client = limiter.client_ip(request.headers(), peer);
+    match limiter.check_request(client) {
+        Ok(()) => next.run(request).await,
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Rate limit key of a client; clients of unknown IP share one.
+fn client_key(prefix: &str, client: Option<IpAddr>) -> String {
+    client.map_or_else(
+        || format!("{prefix}:unknown"),
+        |ip| format!("{prefix}:{ip}"),
+    )
+}
+
+/// Parse an address of a forwarding header, with or without a port.
+fn parse_ip(value: &str) -> Option<IpAddr> {
+    let value = value.trim();
+    value
+        .parse()
+        .ok()
+        .or_else(|| value.parse::<SocketAddr>().ok().map(|addr| addr.ip()))
+}
+
+fn current_timestamp() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use axum::Router;
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use axum::routing::get;
+    use tower::ServiceExt;
+
+    use super::*;
+
+    fn settings(burst_size: u32) -> RateLimitSettings {
+        RateLimitSettings {
+            requests_per_minute: 1000,
+            burst_size,
+            websocket_connects_per_minute: 2,
+            subscribe_messages_per_minute: 2,
+            trusted_proxy_header: None,
+        }
+    }
+
+    fn app(settings: &RateLimitSettings) -> Router {
+        let limiter = Arc::new(RateLimiter::new(Arc::new(MemoryCache::new()), settings));
+        Router::new()
+            .route("/api/v1/stats", get(|| async { "stats" }))
+            .route(HEALTH_PATH, get(|| async { "ok" }))
+            .layer(axum::middleware::from_fn_with_state(
+                limiter,
+                limit_requests,
+            ))
+    }
+
+    async fn get_from(app: &Router, uri: &str, peer: [u8; 4]) -> Response {
+        let mut request = Request::get(uri).body(Body::empty()).unwrap();
+        request
+            .extensions_mut()
+            .insert(ConnectInfo(SocketAddr::from((peer, 40_000))));
+        app.clone().oneshot(request).await.unwrap()
+    }
+
+    /// Sleep until the next burst window starts
+    async fn next_window() {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap();
+        tokio::time::sleep(Duration::from_nanos(
+            1_000_000_000 - u64::from(now.subsec_nanos()) + 10_000_000,
+        ))
+        .await;
+    }
+
+    #[tokio::test]
+    async fn limits_past_burst_and_recovers() {
+        let app = app(&settings(3));
+        let client = [203, 0, 113, 7];
+        next_window().await;
+
+        for _ in 0..3 {
+            let response = get_from(&app, "/api/v1/stats", client).await;
+            assert_eq!(response.status(), StatusCode::OK);
+        }
+        let response = get_from(&app, "/api/v1/stats", client).await;
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()["retry-after"], "1");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["error"]["code"], "RATE_LIMITED");
+
+        // Other clients have their own limits
+        let other = get_from(&app, "/api/v1/stats", [203, 0, 113, 8]).await;
+        assert_eq!(other.status(), StatusCode::OK);
+
+        next_window().await;
+        let response = get_from(&app, "/api/v1/stats", client).await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn health_and_loopback_are_exempt() {
+        let app = app(&settings(1));
+        next_window().await;
+
+        for _ in 0..5 {
+            let health = get_from(&app, HEALTH_PATH, [203, 0, 113, 7]).await;
+            assert_eq!(health.status(), StatusCode::OK);
+            let local = get_from(&app, "/api/v1/stats", [127, 0, 0, 1]).await;
+            assert_eq!(local.status(), StatusCode::OK);
+        }
+    }
+
+    #[test]
+    fn forwarded_client_only_with_trusted_header() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "x-forwarded-for",
+            "10.0.0.1, 198.51.100.4:443".parse().unwrap(),
+        );
+        let peer = Some(IpAddr::from([127, 0, 0, 1]));
+
+        let untrusted = RateLimiter::new(Arc::new(MemoryCache::new()), &settings(1));
+        assert_eq!(
+            untrusted.client_ip(&headers, peer),
+            peer,
+            "header is spoofable"
+        );
+
+        let trusted = RateLimiter::new(
+            Arc::new(MemoryCache::new()),
+            &RateLimitSettings {
+                trusted_proxy_header: Some("X-Forwarded-For".into()),
+                ..settings(1)
+            },
+        );
+        let client = trusted.client_ip(&headers, peer);
+        assert_eq!(client, Some(IpAddr::from([198, 51, 100, 4])));
+        assert_eq!(trusted.client_ip(&HeaderMap::new(), peer), peer);
+
+        // A proxy on localhost does not exempt its clients
+        assert!(trusted.check_request(client).is_ok());
+        assert!(trusted.check_request(client).is_err());
+    }
+
+    #[test]
+    fn websocket_limits() {
+        let limiter = RateLimiter::new(Arc::new(MemoryCache::new()), &settings(1));
+        let client = Some(IpAddr::from([203, 0, 113, 7]));
+
+        // Connection attempts are limited apart from requests
+        assert!(limiter.check_request(client).is_ok());
+        assert!(limiter.check_ws_connect(client).is_ok());
+        assert!(limiter.check_ws_connect(client).is_ok());
+        assert!(matches!(
+            limiter.check_ws_connect(client),
+            Err(ApiError::RateLimited { retry_after_secs }) if retry_after_secs <= 60
+        ));
+
+        assert!(limiter.check_subscribe(1).is_ok());
+        assert!(limiter.check_subscribe(1).is_ok());
+        assert!(limiter.check_subscribe(1).is_err());
+        assert!(
+            limiter.check_subscribe(2).is_ok(),
+            "limits are per connection"
+        );
+    }
+}
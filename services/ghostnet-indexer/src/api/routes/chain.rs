@@ -0,0 +1,67 @@
+//! Contract read routes.
+
+use axum::Json;
+use axum::extract::{Path, Query, State};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::api::ApiState;
+use crate::error::ApiError;
+
+/// Query parameters for `GET /chain/read/:contract/:method`.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ChainReadQuery {
+    /// Arguments of the view, comma-separated and in order (e.g.
+    /// `0xowner,0xspender`). Addresses are hex, integers decimal or hex.
+    pub args: Option<String>,
+}
+
+/// A view's return value.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChainReadResponse {
+    /// Contract called, e.g. `data_token`.
+    pub contract: String,
+    /// View called, e.g. `allowance`.
+    pub method: String,
+    /// Arguments the view was called with.
+    pub args: Vec<String>,
+    /// Return value, decoded with the contract's ABI.
+    pub result: Value,
+}
+
+/// `GET /chain/read/:contract/:method`
+///
+/// Calls one of the allowlisted [views](crate::api::chain_read::VIEWS),
+/// served from cache for the view's TTL.
+///
+/// # Errors
+///
+/// Returns `404` if the contract or view is unknown or contract reads are
+/// not configured, `400` if the arguments do not match the view's
+/// parameters, and `502` with the `upstream` failure if the call fails.
+pub async fn read_contract<S: Send + Sync>(
+    State(state): State<ApiState<S>>,
+    Path((contract, method)): Path<(String, String)>,
+    Query(query): Query<ChainReadQuery>,
+) -> Result<Json<ChainReadResponse>, ApiError> {
+    let reader = state
+        .chain_reader
+        .as_ref()
+        .ok_or_else(|| ApiError::NotFound("contract reads".into()))?;
+    let args: Vec<String> = query
+        .args
+        .iter()
+        .flat_map(|args| args.split(','))
+        .map(str::trim)
+        .filter(|arg| !arg.is_empty())
+        .map(String::from)
+        .collect();
+
+    let result = reader.read(&contract, &method, &args).await?;
+    Ok(Json(ChainReadResponse {
+        contract,
+        method,
+        args,
+        result,
+    }))
+}
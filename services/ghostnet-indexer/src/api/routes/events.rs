@@ -0,0 +1,600 @@
+//! Event stream over WebSocket.
+//!
+//! # Protocol
+//!
+//! Clients subscribe to topics with JSON text messages:
+//!
+//! ```json
+//! {"type": "subscribe", "topic": "positions", "from_sequence": 42, "schema_version": 2}
+//! {"type": "unsubscribe", "topic": "positions"}
+//! ```
+//!
+//! Every event carries its topic's sequence, the same Iggy consumers see in
+//! the `sequence` header, and the [wire envelope](crate::streaming::Envelope)
+//! as published:
+//!
+//! ```json
+//! {"type": "event", "topic": "positions", "sequence": 42, "event": {"schema_version": 2, ...}}
+//! ```
+//!
+//! A subscription delivers one schema version, `schema_version` or else the
+//! oldest one published for the topic. Asking for a version that is not
+//! published is an error. While a topic publishes several versions, each
+//! event is logged once per version, so a client sees gaps in the
+//! sequences.
+//!
+//! With `from_sequence`, the events from that sequence on are replayed
+//! first, then `{"type": "replay_complete", "topic": ..., "sequence": 45}`
+//! marks the seam: the last replayed sequence, after which only live events
+//! follow. Without `from_sequence` nothing is replayed and the marker holds
+//! the current sequence. A reconnecting client resumes with the last
+//! sequence it saw plus one.
+//!
+//! If the events from `from_sequence` are no longer kept, the client gets
+//! `{"type": "replay_unavailable", "topic": ..., "current_sequence": 90}`
+//! instead, followed by the live events after `current_sequence`; it should
+//! reload what it needs over REST.
+//!
+//! Invalid messages are answered with `{"type": "error", "message": ...}`.
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{ConnectInfo, State};
+use axum::http::HeaderMap;
+use axum::response::Response;
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tracing::{debug, warn};
+
+use crate::api::{ApiState, RateLimiter};
+use crate::error::ApiError;
+use crate::streaming::{EventLog, Subscription, SubscriptionItem, Topic, WireSchemas};
+
+/// Messages queued per connection before subscriptions wait for the client.
+const OUTGOING_CAPACITY: usize = 256;
+
+/// ID of the next connection, for per-connection rate limits.
+static NEXT_CONNECTION_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Message from a client.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientMessage {
+    /// Subscribe to `topic`, replaying from `from_sequence` if given.
+    ///
+    /// Subscribing to a topic again replaces its subscription.
+    Subscribe {
+        /// Topic name, e.g. `positions`.
+        topic: String,
+        /// First sequence to replay.
+        #[serde(default)]
+        from_sequence: Option<u64>,
+        /// Schema version of the events delivered.
+        #[serde(default)]
+        schema_version: Option<u32>,
+    },
+    /// Stop receiving the events of `topic`.
+    Unsubscribe {
+        /// Topic name.
+        topic: String,
+    },
+}
+
+/// Message to a client.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServerMessage {
+    /// A published event.
+    Event {
+        /// Topic of the event.
+        topic: String,
+        /// Position of the event within its topic.
+        sequence: u64,
+        /// The event, as published.
+        event: serde_json::Value,
+    },
+    /// The replay is complete; live events follow.
+    ReplayComplete {
+        /// Topic replayed.
+        topic: String,
+        /// Last sequence replayed, or the current one if none was.
+        sequence: u64,
+    },
+    /// The requested sequence is no longer kept; live events follow.
+    ReplayUnavailable {
+        /// Topic subscribed to.
+        topic: String,
+        /// Latest published sequence.
+        current_sequence: u64,
+    },
+    /// The last client message was rejected.
+    Error {
+        /// What was wrong.
+        message: String,
+    },
+}
+
+impl ServerMessage {
+    /// Message delivering `item` of `topic`, if its event is valid JSON in
+    /// `schema_version`.
+    ///
+    /// Events that are not wire envelopes are delivered as they are.
+    fn from_item(topic: &str, item: SubscriptionItem, schema_version: u32) -> Option<Self> {
+        let topic = topic.to_string();
+        Some(match item {
+            SubscriptionItem::Event(event) => {
+                let payload: serde_json::Value = match serde_json::from_slice(&event.payload) {
+                    Ok(payload) => payload,
+                    Err(e) => {
+                        let sequence = event.sequence;
+                        warn!(%topic, sequence, error = %e, "Skipping event that is not JSON");
+                        return None;
+                    }
+                };
+                if payload
+                    .get("schema_version")
+                    .and_then(serde_json::Value::as_u64)
+                    .is_some_and(|version| version != u64::from(schema_version))
+                {
+                    return None;
+                }
+                Self::Event {
+                    topic,
+                    sequence: event.sequence,
+                    event: payload,
+                }
+            }
+            SubscriptionItem::ReplayComplete { sequence } => {
+                Self::ReplayComplete { topic, sequence }
+            }
+            SubscriptionItem::ReplayUnavailable { current_sequence } => Self::ReplayUnavailable {
+                topic,
+                current_sequence,
+            },
+        })
+    }
+
+    fn error(message: impl Into<String>) -> Self {
+        Self::Error {
+            message: message.into(),
+        }
+    }
+}
+
+/// `GET /ws`
+///
+/// Upgrades to a WebSocket streaming the published events, see the
+/// [module docs](self) for the protocol.
+///
+/// # Errors
+///
+/// Returns `404` without an event log and `429` if the client opens
+/// connections too quickly.
+pub async fn stream_events<S: Send + Sync + 'static>(
+    State(state): State<ApiState<S>>,
+    peer: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
+    upgrade: WebSocketUpgrade,
+) -> Result<Response, ApiError> {
+    let log = state
+        .event_log
+        .clone()
+        .ok_or_else(|| ApiError::NotFound("event stream".into()))?;
+    let schemas = state.wire_schemas.clone().unwrap_or_default();
+    let limiter = state.rate_limiter;
+    if let Some(limiter) = &limiter {
+        let client = limiter.client_ip(&headers, peer.map(|ConnectInfo(addr)| addr.ip()));
+        limiter.check_ws_connect(client)?;
+    }
+
+    let connection_id = NEXT_CONNECTION_ID.fetch_add(1, Ordering::Relaxed);
+    Ok(upgrade.on_upgrade(move |socket| {
+        Session {
+            log,
+            schemas,
+            limiter,
+            connection_id,
+            subscriptions: HashMap::new(),
+        }
+        .run(socket)
+    }))
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// SESSION
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// One client connection.
+struct Session {
+    /// Log the events come from.
+    log: Arc<EventLog>,
+    /// Schema versions clients can subscribe to.
+    schemas: Arc<WireSchemas>,
+    /// Limits subscribe messages per connection.
+    limiter: Option<Arc<RateLimiter>>,
+    /// Connection ID for the rate limiter.
+    connection_id: u64,
+    /// Tasks forwarding the events of each subscribed topic.
+    subscriptions: HashMap<Topic, JoinHandle<()>>,
+}
+
+impl Session {
+    /// Serve the client until it disconnects.
+    async fn run(mut self, mut socket: WebSocket) {
+        let (outgoing, mut queued) = mpsc::channel(OUTGOING_CAPACITY);
+        loop {
+            let reply = tokio::select! {
+                incoming = socket.recv() => match incoming {
+                    Some(Ok(Message::Text(text))) => self.handle(&text, &outgoing).await,
+                    // Pings are answered by axum
+                    Some(Ok(Message::Binary(_) | Message::Ping(_) | Message::Pong(_))) => None,
+                    Some(Ok(Message::Close(_)) | Err(_)) | None => break,
+                },
+                Some(message) = queued.recv() => Some(message),
+            };
+            let Some(reply) = reply else {
+                continue;
+            };
+            let Ok(text) = serde_json::to_string(&reply) else {
+                continue;
+            };
+            if socket.send(Message::Text(text)).await.is_err() {
+                break;
+            }
+        }
+
+        for task in self.subscriptions.into_values() {
+            task.abort();
+        }
+        debug!(
+            connection_id = self.connection_id,
+            "WebSocket client disconnected"
+        );
+    }
+
+    /// Handle a client message, returning the reply, if any.
+    async fn handle(
+        &mut self,
+        text: &str,
+        outgoing: &mpsc::Sender<ServerMessage>,
+    ) -> Option<ServerMessage> {
+        let message = match serde_json::from_str::<ClientMessage>(text) {
+            Ok(message) => message,
+            Err(e) => return Some(ServerMessage::error(format!("invalid message: {e}"))),
+        };
+        match message {
+            ClientMessage::Subscribe {
+                topic,
+                from_sequence,
+                schema_version,
+            } => {
+                if let Some(limiter) = &self.limiter
+                    && let Err(e) = limiter.check_subscribe(self.connection_id)
+                {
+                    return Some(ServerMessage::error(e.to_string()));
+                }
+                let Some(topic) = Topic::from_name(&topic) else {
+                    return Some(ServerMessage::error(format!("unknown topic {topic:?}")));
+                };
+                let schema_version = match self.schemas.negotiate(topic, schema_version) {
+                    Ok(version) => version,
+                    Err(e) => return Some(ServerMessage::error(e.to_string())),
+                };
+                let subscription = match self.log.subscribe(topic.as_str(), from_sequence).await {
+                    Ok(subscription) => subscription,
+                    Err(e) => {
+                        warn!(%topic, error = %e, "Subscribing to events failed");
+                        return Some(ServerMessage::error(format!("cannot subscribe to {topic}")));
+                    }
+                };
+                let task = tokio::spawn(forward(subscription, schema_version, outgoing.clone()));
+                if let Some(replaced) = self.subscriptions.insert(topic, task) {
+                    replaced.abort();
+                }
+                None
+            }
+            ClientMessage::Unsubscribe { topic } => {
+                if let Some(task) =
+                    Topic::from_name(&topic).and_then(|topic| self.subscriptions.remove(&topic))
+                {
+                    task.abort();
+                }
+                None
+            }
+        }
+    }
+}
+
+/// Queue the items of `subscription` in `schema_version` until it ends or
+/// the client is gone.
+async fn forward(
+    mut subscription: Subscription,
+    schema_version: u32,
+    outgoing: mpsc::Sender<ServerMessage>,
+) {
+    loop {
+        let item = match subscription.next().await {
+            Ok(Some(item)) => item,
+            Ok(None) => return,
+            Err(e) => {
+                warn!(topic = subscription.topic(), error = %e, "Event subscription failed");
+                let message = format!("subscription to {} failed", subscription.topic());
+                let _ = outgoing.send(ServerMessage::error(message)).await;
+                return;
+            }
+        };
+        let Some(message) = ServerMessage::from_item(subscription.topic(), item, schema_version)
+        else {
+            continue;
+        };
+        if outgoing.send(message).await.is_err() {
+            return;
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::time::Duration;
+
+    use alloy::primitives::{Address, B256, U256};
+    use async_trait::async_trait;
+    use bytes::Bytes;
+    use futures_util::{SinkExt, StreamExt};
+    use serde_json::{Value, json};
+    use tokio::net::TcpStream;
+    use tokio_tungstenite::tungstenite;
+    use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+
+    use super::*;
+    use crate::config::{LeaderboardSettings, TokenFlowSettings, WebSocketSettings};
+    use crate::error::Result;
+    use crate::indexer::LeaderboardRefresher;
+    use crate::ports::{EventPublisher, IdentifiedMessage, LeaderboardStore};
+    use crate::store::MemoryCache;
+    use crate::streaming::{MemoryEventLog, NoOpPublisher, SequencedPublisher};
+    use crate::types::entities::LeaderboardEntry;
+    use crate::types::enums::{LeaderboardType, Level};
+    use crate::types::events::{EventMetadata, GhostnetEvent, JackedInEvent};
+
+    type Client = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+    /// Store without leaderboards; the event stream needs none.
+    #[derive(Debug)]
+    struct EmptyStore;
+
+    #[async_trait]
+    impl LeaderboardStore for EmptyStore {
+        async fn get_leaderboard(
+            &self,
+            _: LeaderboardType,
+            _: u32,
+        ) -> Result<Vec<LeaderboardEntry>> {
+            Ok(vec![])
+        }
+    }
+
+    /// Serve the event stream of a fresh log, returning its address and a
+    /// publisher feeding the log.
+    async fn serve() -> (SocketAddr, SequencedPublisher<NoOpPublisher>) {
+        let settings = WebSocketSettings {
+            max_connections: 10,
+            ping_interval_ms: 30_000,
+            pong_timeout_ms: 10_000,
+            replay_retention_secs: 3600,
+            replay_max_events: 10_000,
+        };
+        let log = Arc::new(EventLog::new(
+            Arc::new(MemoryEventLog::default()),
+            &settings,
+        ));
+        let store = Arc::new(EmptyStore);
+        let leaderboard = LeaderboardSettings::default();
+        let refresher = Arc::new(LeaderboardRefresher::new(
+            Arc::clone(&store),
+            Arc::new(MemoryCache::new()),
+            &leaderboard,
+        ));
+        let state = ApiState::new(
+            store,
+            refresher,
+            &leaderboard,
+            &TokenFlowSettings::default(),
+        )
+        .with_event_log(Arc::clone(&log));
+        let app = axum::Router::new()
+            .route("/ws", axum::routing::get(stream_events::<EmptyStore>))
+            .with_state(state);
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        (addr, SequencedPublisher::new(Arc::new(NoOpPublisher), log))
+    }
+
+    async fn connect(addr: SocketAddr) -> Client {
+        let (client, _) = tokio_tungstenite::connect_async(format!("ws://{addr}/ws"))
+            .await
+            .unwrap();
+        client
+    }
+
+    async fn send(client: &mut Client, message: Value) {
+        client
+            .send(tungstenite::Message::text(message.to_string()))
+            .await
+            .unwrap();
+    }
+
+    /// Receive the next message, failing after a second.
+    async fn receive(client: &mut Client) -> Value {
+        let message = tokio::time::timeout(Duration::from_secs(1), client.next())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        serde_json::from_str(message.to_text().unwrap()).unwrap()
+    }
+
+    /// Publish events `ids` to `positions`, one call each.
+    async fn publish(publisher: &SequencedPublisher<NoOpPublisher>, ids: std::ops::Range<u128>) {
+        for id in ids {
+            let message = IdentifiedMessage {
+                id,
+                payload: Bytes::from(json!({ "id": id.to_string() }).to_string()),
+                sequence: None,
+            };
+            publisher
+                .publish_acknowledged("positions", &[message])
+                .await
+                .unwrap();
+        }
+    }
+
+    fn event(sequence: u64, id: u128) -> Value {
+        json!({
+            "type": "event",
+            "topic": "positions",
+            "sequence": sequence,
+            "event": { "id": id.to_string() },
+        })
+    }
+
+    #[tokio::test]
+    async fn reconnecting_clients_get_exactly_the_missed_events_before_live_ones() {
+        let (addr, publisher) = serve().await;
+
+        let mut client = connect(addr).await;
+        send(
+            &mut client,
+            json!({ "type": "subscribe", "topic": "positions" }),
+        )
+        .await;
+        assert_eq!(
+            receive(&mut client).await,
+            json!({ "type": "replay_complete", "topic": "positions", "sequence": 0 })
+        );
+        publish(&publisher, 0..3).await;
+        for sequence in 1..=3 {
+            assert_eq!(
+                receive(&mut client).await,
+                event(sequence, u128::from(sequence - 1))
+            );
+        }
+        client.close(None).await.unwrap();
+
+        // Missed while disconnected
+        publish(&publisher, 3..5).await;
+
+        let mut client = connect(addr).await;
+        send(
+            &mut client,
+            json!({ "type": "subscribe", "topic": "positions", "from_sequence": 4 }),
+        )
+        .await;
+        assert_eq!(receive(&mut client).await, event(4, 3));
+        assert_eq!(receive(&mut client).await, event(5, 4));
+        assert_eq!(
+            receive(&mut client).await,
+            json!({ "type": "replay_complete", "topic": "positions", "sequence": 5 })
+        );
+
+        publish(&publisher, 5..6).await;
+        assert_eq!(receive(&mut client).await, event(6, 5));
+        assert!(
+            tokio::time::timeout(Duration::from_millis(50), client.next())
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn clients_receive_the_schema_version_they_subscribed_to() {
+        let (addr, publisher) = serve().await;
+        let mut client = connect(addr).await;
+        send(
+            &mut client,
+            json!({ "type": "subscribe", "topic": "positions", "schema_version": 2 }),
+        )
+        .await;
+        assert_eq!(receive(&mut client).await["type"], "replay_complete");
+
+        // Published in versions 1 and 2 during the deprecation window
+        let jacked_in = GhostnetEvent::JackedIn(JackedInEvent {
+            meta: EventMetadata {
+                block_number: 1,
+                block_hash: B256::ZERO,
+                tx_hash: B256::ZERO,
+                tx_index: 0,
+                log_index: 0,
+                timestamp: chrono::Utc::now(),
+                contract: Address::ZERO,
+                chain_id: 6343,
+                tx_function: None,
+                tx_from: None,
+            },
+            user: Address::ZERO,
+            amount: U256::from(10).pow(U256::from(18)),
+            level: Level::Vault,
+            new_total: U256::from(10).pow(U256::from(18)),
+        });
+        let messages: Vec<IdentifiedMessage> = WireSchemas::default()
+            .encode(&jacked_in)
+            .unwrap()
+            .into_iter()
+            .map(|message| message.identified(1))
+            .collect();
+        publisher
+            .publish_acknowledged("positions", &messages)
+            .await
+            .unwrap();
+
+        let event = receive(&mut client).await;
+        assert_eq!(event["sequence"], 2);
+        assert_eq!(event["event"]["schema_version"], 2);
+        assert_eq!(event["event"]["sequence"], 2);
+        assert_eq!(event["event"]["event"]["amount"], "1");
+        assert!(
+            tokio::time::timeout(Duration::from_millis(50), client.next())
+                .await
+                .is_err()
+        );
+
+        send(
+            &mut client,
+            json!({ "type": "subscribe", "topic": "positions", "schema_version": 3 }),
+        )
+        .await;
+        assert_eq!(
+            receive(&mut client).await,
+            json!({
+                "type": "error",
+                "message": "schema version 3 is not published for topic positions (published: [1, 2])",
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn invalid_subscriptions_are_rejected() {
+        let (addr, _) = serve().await;
+        let mut client = connect(addr).await;
+
+        send(&mut client, json!({ "type": "subscribe", "topic": "nope" })).await;
+        assert_eq!(
+            receive(&mut client).await,
+            json!({ "type": "error", "message": "unknown topic \"nope\"" })
+        );
+        send(&mut client, json!({ "type": "jack_in" })).await;
+        assert_eq!(receive(&mut client).await["type"], "error");
+    }
+}
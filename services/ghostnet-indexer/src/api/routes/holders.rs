@@ -0,0 +1,274 @@
+//! DATA holder routes.
+
+use axum::Json;
+use axum::extract::{Path, Query, State};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::api::ApiState;
+use crate::error::ApiError;
+use crate::ports::HolderStore;
+use crate::types::entities::{HolderBalance, HolderSort};
+use crate::types::primitives::{EthAddress, TokenAmount};
+
+/// Holders returned when a listing request has no `limit`.
+pub const DEFAULT_HOLDERS_LIMIT: u32 = 100;
+
+/// Most holders returned by a single listing request.
+pub const MAX_HOLDERS_LIMIT: u32 = 1000;
+
+/// Decimals of the DATA token.
+const DATA_TOKEN_DECIMALS: u8 = 18;
+
+/// Query parameters for `GET /token/holders`.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct HoldersQuery {
+    /// Order of the holders (`balance`, `first_seen` or `last_active`,
+    /// defaults to `balance`).
+    pub sort: Option<HolderSort>,
+    /// Number of holders to return (defaults to [`DEFAULT_HOLDERS_LIMIT`],
+    /// capped at [`MAX_HOLDERS_LIMIT`]).
+    pub limit: Option<u32>,
+}
+
+/// A holder's DATA balance.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HolderBody {
+    /// The address.
+    pub address: EthAddress,
+    /// Balance in DATA.
+    pub balance: TokenAmount,
+    /// Timestamp of the first transfer seen.
+    pub first_seen: DateTime<Utc>,
+    /// Timestamp of the last transfer.
+    pub last_active: DateTime<Utc>,
+    /// Whether the balance is known to be off until it is read from the
+    /// chain again.
+    pub needs_reconciliation: bool,
+    /// When the balance was last compared with the chain.
+    pub reconciled_at: Option<DateTime<Utc>>,
+}
+
+impl From<HolderBalance> for HolderBody {
+    fn from(holder: HolderBalance) -> Self {
+        Self {
+            address: holder.address,
+            balance: TokenAmount::from_wei(holder.balance, DATA_TOKEN_DECIMALS),
+            first_seen: holder.first_seen,
+            last_active: holder.last_active,
+            needs_reconciliation: holder.needs_reconciliation,
+            reconciled_at: holder.reconciled_at,
+        }
+    }
+}
+
+/// Response body for `GET /token/holders`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HoldersResponse {
+    /// Order of `holders`.
+    pub sort: HolderSort,
+    /// Addresses holding DATA, in the order of `sort`.
+    pub holders: Vec<HolderBody>,
+}
+
+/// `GET /token/holders?sort=&limit=`
+///
+/// # Errors
+///
+/// Returns `400` for an unknown `sort` or a zero `limit`.
+pub async fn list_holders<S: HolderStore>(
+    State(state): State<ApiState<S>>,
+    Query(query): Query<HoldersQuery>,
+) -> Result<Json<HoldersResponse>, ApiError> {
+    let sort = query.sort.unwrap_or_default();
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_HOLDERS_LIMIT)
+        .min(MAX_HOLDERS_LIMIT);
+    if limit == 0 {
+        return Err(ApiError::BadRequest("limit must be at least 1".into()));
+    }
+
+    let holders = state.store.get_top_holders(sort, limit).await?;
+    Ok(Json(HoldersResponse {
+        sort,
+        holders: holders.into_iter().map(HolderBody::from).collect(),
+    }))
+}
+
+/// `GET /token/holders/:address`
+///
+/// Addresses that hold nothing now but held DATA before are returned with
+/// a zero balance.
+///
+/// # Errors
+///
+/// Returns `400` for a malformed `address` and `404` for an address that
+/// never received DATA.
+pub async fn get_holder<S: HolderStore>(
+    State(state): State<ApiState<S>>,
+    Path(address): Path<String>,
+) -> Result<Json<HolderBody>, ApiError> {
+    let address =
+        EthAddress::from_hex(&address).map_err(|e| ApiError::BadRequest(e.to_string()))?;
+
+    let holder = state
+        .store
+        .get_holder(&address)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("holder {address}")))?;
+    Ok(Json(holder.into()))
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use alloy::primitives::U256;
+    use async_trait::async_trait;
+
+    use super::*;
+    use crate::config::{LeaderboardSettings, TokenFlowSettings};
+    use crate::error::Result;
+    use crate::indexer::LeaderboardRefresher;
+    use crate::ports::LeaderboardStore;
+    use crate::store::MemoryCache;
+    use crate::types::entities::LeaderboardEntry;
+    use crate::types::enums::LeaderboardType;
+
+    /// Store with one holder of 1.5 DATA, recording listing queries.
+    #[derive(Debug, Default)]
+    struct FixedStore {
+        queries: Mutex<Vec<(HolderSort, u32)>>,
+    }
+
+    fn holder() -> HolderBalance {
+        let mut holder = HolderBalance::new(EthAddress::new([0xaa; 20]), Utc::now());
+        holder.balance = U256::from(1_500_000_000_000_000_000_u64);
+        holder
+    }
+
+    #[async_trait]
+    impl HolderStore for FixedStore {
+        async fn get_holder(&self, address: &EthAddress) -> Result<Option<HolderBalance>> {
+            Ok(Some(holder()).filter(|holder| holder.address == *address))
+        }
+
+        async fn save_holder(&self, _: &HolderBalance) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_top_holders(
+            &self,
+            sort: HolderSort,
+            limit: u32,
+        ) -> Result<Vec<HolderBalance>> {
+            self.queries.lock().unwrap().push((sort, limit));
+            Ok(vec![holder()])
+        }
+
+        async fn count_holders(&self) -> Result<u64> {
+            Ok(1)
+        }
+
+        async fn get_holders_to_reconcile(&self, _: u32) -> Result<Vec<HolderBalance>> {
+            Ok(vec![])
+        }
+
+        async fn reconcile_holder(
+            &self,
+            _: &HolderBalance,
+            _: U256,
+            _: DateTime<Utc>,
+        ) -> Result<bool> {
+            Ok(false)
+        }
+    }
+
+    #[async_trait]
+    impl LeaderboardStore for FixedStore {
+        async fn get_leaderboard(
+            &self,
+            _: LeaderboardType,
+            _: u32,
+        ) -> Result<Vec<LeaderboardEntry>> {
+            Ok(vec![])
+        }
+    }
+
+    fn state() -> (ApiState<FixedStore>, Arc<FixedStore>) {
+        let store = Arc::new(FixedStore::default());
+        let leaderboard = LeaderboardSettings::default();
+        let refresher = Arc::new(LeaderboardRefresher::new(
+            Arc::clone(&store),
+            Arc::new(MemoryCache::new()),
+            &leaderboard,
+        ));
+        let state = ApiState::new(
+            Arc::clone(&store),
+            refresher,
+            &leaderboard,
+            &TokenFlowSettings::default(),
+        );
+        (state, store)
+    }
+
+    #[tokio::test]
+    async fn holders_are_listed_in_data() {
+        let (state, store) = state();
+        let query = HoldersQuery {
+            sort: Some(HolderSort::LastActive),
+            limit: Some(5000),
+        };
+
+        let Json(response) = list_holders(State(state), Query(query)).await.unwrap();
+
+        assert_eq!(
+            *store.queries.lock().unwrap(),
+            [(HolderSort::LastActive, MAX_HOLDERS_LIMIT)]
+        );
+        let body = serde_json::to_value(&response).unwrap();
+        assert_eq!(body["sort"], "last_active");
+        assert_eq!(body["holders"][0]["balance"], "1.5");
+    }
+
+    #[tokio::test]
+    async fn listing_defaults_to_largest_balances() {
+        let (state, store) = state();
+
+        let listed = list_holders(State(state.clone()), Query(HoldersQuery::default())).await;
+        let zero = HoldersQuery {
+            limit: Some(0),
+            ..HoldersQuery::default()
+        };
+        let rejected = list_holders(State(state), Query(zero)).await;
+
+        assert_eq!(
+            *store.queries.lock().unwrap(),
+            [(HolderSort::Balance, DEFAULT_HOLDERS_LIMIT)]
+        );
+        assert!(listed.is_ok());
+        assert!(matches!(rejected, Err(ApiError::BadRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn holder_lookup_by_address() {
+        let (state, _) = state();
+        let address = EthAddress::new([0xaa; 20]).to_hex();
+
+        let Json(holder) = get_holder(State(state.clone()), Path(address))
+            .await
+            .unwrap();
+        assert_eq!(holder.balance, TokenAmount::parse("1.5").unwrap());
+
+        let unknown = get_holder(State(state.clone()), Path(EthAddress::ZERO.to_hex())).await;
+        assert!(matches!(unknown, Err(ApiError::NotFound(_))));
+        let malformed = get_holder(State(state), Path("0x12".into())).await;
+        assert!(matches!(malformed, Err(ApiError::BadRequest(_))));
+    }
+}
@@ -0,0 +1,479 @@
+//! Leaderboard routes.
+
+use axum::Json;
+use axum::extract::{Path, Query, State};
+use serde::{Deserialize, Serialize};
+
+use crate::api::ApiState;
+use crate::error::ApiError;
+use crate::ports::LeaderboardStore;
+use crate::types::entities::LeaderboardEntry;
+use crate::types::enums::LeaderboardType;
+
+/// Query parameters for `GET /leaderboard/:type`.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct LeaderboardQuery {
+    /// Number of entries to return (defaults to `leaderboard.default_limit`,
+    /// capped at `leaderboard.max_entries`).
+    pub limit: Option<u32>,
+}
+
+/// Response body for `GET /leaderboard/:type`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LeaderboardResponse {
+    /// The leaderboard that was requested.
+    pub leaderboard: LeaderboardType,
+    /// Ranked entries, best first.
+    pub entries: Vec<LeaderboardEntry>,
+}
+
+/// `GET /leaderboard/:type?limit=`
+///
+/// # Errors
+///
+/// Returns `400` for an unknown leaderboard type or a zero `limit`.
+pub async fn get_leaderboard<S: LeaderboardStore>(
+    State(state): State<ApiState<S>>,
+    Path(leaderboard): Path<String>,
+    Query(query): Query<LeaderboardQuery>,
+) -> Result<Json<LeaderboardResponse>, ApiError> {
+    let leaderboard: LeaderboardType = leaderboard.parse().map_err(ApiError::BadRequest)?;
+    let limit = query.limit.unwrap_or(state.leaderboard_default_limit);
+    if limit == 0 {
+        return Err(ApiError::BadRequest("limit must be at least 1".into()));
+    }
+
+    let entries = state.leaderboards.get(leaderboard, limit).await?;
+    Ok(Json(LeaderboardResponse {
+        leaderboard,
+        entries,
+    }))
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use alloy::primitives::U256;
+    use async_trait::async_trait;
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use bigdecimal::BigDecimal;
+    use chrono::{DateTime, Utc};
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::api::router;
+    use crate::config::{LeaderboardSettings, TokenFlowSettings};
+    use crate::error::{InfraError, Result};
+    use crate::indexer::LeaderboardRefresher;
+    use crate::ports::{
+        DeathStore, HolderStore, MarketStore, PositionStore, ScanStore, StatsStore, TokenFlowStore,
+    };
+    use crate::store::MemoryCache;
+    use crate::types::entities::{
+        AddressFlows, Bet, BurnRate, CascadeEarnings, CascadeShare, Death, ExitStreakCount,
+        GlobalStats, GlobalStatsDelta, HistoryBucket, HistoryCursor, HolderBalance, HolderSort,
+        LevelHistoryPoint, LevelScanStats, LevelStats, LevelStatsDelta, LevelSurvival, OutboxEvent,
+        Page, Position, PositionFilter, PositionHistoryEntry, Round, RoundSettlement, Scan,
+        ScanFinalizationData, TokenFlowDelta, TokenTransfer,
+    };
+    use crate::types::enums::Level;
+    use crate::types::primitives::{BlockNumber, EthAddress, TokenAmount};
+
+    /// Store with three ghost streak entries; other leaderboards are empty.
+    #[derive(Debug, Default)]
+    struct FixedStore {
+        queries: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl LeaderboardStore for FixedStore {
+        async fn get_leaderboard(
+            &self,
+            leaderboard: LeaderboardType,
+            limit: u32,
+        ) -> Result<Vec<LeaderboardEntry>> {
+            self.queries.fetch_add(1, Ordering::SeqCst);
+            if leaderboard != LeaderboardType::GhostStreak {
+                return Ok(vec![]);
+            }
+            Ok([(1, 0xaa, 42), (2, 0x11, 17), (3, 0x22, 17)]
+                .into_iter()
+                .take(limit as usize)
+                .map(|(rank, byte, streak)| LeaderboardEntry {
+                    rank,
+                    user_address: EthAddress::new([byte; 20]),
+                    score: TokenAmount::new(BigDecimal::from(streak)).unwrap(),
+                    metadata: Some(serde_json::json!({ "level": 3 })),
+                })
+                .collect())
+        }
+    }
+
+    #[async_trait]
+    impl TokenFlowStore for FixedStore {
+        async fn record_transfer(&self, _: &TokenTransfer) -> Result<()> {
+            Ok(())
+        }
+
+        async fn record_token_flows(&self, _: DateTime<Utc>, _: &TokenFlowDelta) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_burn_rate(&self, _: Duration) -> Result<BurnRate> {
+            Err(InfraError::NotFound.into())
+        }
+
+        async fn get_address_flows(&self, _: &EthAddress, _: Duration) -> Result<AddressFlows> {
+            Err(InfraError::NotFound.into())
+        }
+    }
+
+    #[async_trait]
+    impl ScanStore for FixedStore {
+        async fn save_scan(&self, _: &Scan) -> Result<()> {
+            Ok(())
+        }
+
+        async fn finalize_scan(&self, _: &str, _: ScanFinalizationData) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_recent_scans(&self, _: Level, _: u32) -> Result<Vec<Scan>> {
+            Ok(vec![])
+        }
+
+        async fn get_scan_by_id(&self, _: &str) -> Result<Option<Scan>> {
+            Ok(None)
+        }
+
+        async fn get_pending_scans(&self) -> Result<Vec<Scan>> {
+            Ok(vec![])
+        }
+
+        async fn link_deaths_to_scan(&self, _: &str, _: &[Uuid]) -> Result<u64> {
+            Ok(0)
+        }
+    }
+
+    #[async_trait]
+    impl DeathStore for FixedStore {
+        async fn record_deaths(&self, _: &[Death]) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_deaths_for_scan(&self, _: &str) -> Result<Vec<Death>> {
+            Ok(vec![])
+        }
+
+        async fn get_user_deaths(&self, _: &EthAddress, _: u32) -> Result<Vec<Death>> {
+            Ok(vec![])
+        }
+
+        async fn count_deaths_by_level(&self, _: Level) -> Result<u64> {
+            Ok(0)
+        }
+
+        async fn get_recent_deaths(&self, _: u32) -> Result<Vec<Death>> {
+            Ok(vec![])
+        }
+
+        async fn record_cascade_shares(&self, _: &[CascadeShare]) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_cascade_earnings(&self, _: &EthAddress, _: u32) -> Result<CascadeEarnings> {
+            Ok(CascadeEarnings {
+                total: TokenAmount::zero(),
+                scans: vec![],
+            })
+        }
+    }
+
+    #[async_trait]
+    impl PositionStore for FixedStore {
+        async fn get_active_position(&self, _: &EthAddress) -> Result<Option<Position>> {
+            Ok(None)
+        }
+
+        async fn save_position(&self, _: &Position) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_at_risk_positions(&self, _: Level, _: u32) -> Result<Vec<Position>> {
+            Ok(vec![])
+        }
+
+        async fn append_history(&self, _: &PositionHistoryEntry) -> Result<()> {
+            Ok(())
+        }
+
+        async fn save_position_with_history(
+            &self,
+            _: &Position,
+            _: &PositionHistoryEntry,
+            _: &[OutboxEvent],
+        ) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_history(
+            &self,
+            _: &EthAddress,
+            _: u32,
+            _: Option<HistoryCursor>,
+        ) -> Result<Vec<PositionHistoryEntry>> {
+            Ok(vec![])
+        }
+
+        async fn get_position_by_id(&self, _: &Uuid) -> Result<Option<Position>> {
+            Ok(None)
+        }
+
+        async fn get_positions_by_level(&self, _: Level) -> Result<Vec<Position>> {
+            Ok(vec![])
+        }
+
+        async fn query(&self, _: &PositionFilter, _: u32) -> Result<Page<Position>> {
+            Ok(Page {
+                items: vec![],
+                next_cursor: None,
+            })
+        }
+
+        async fn count_positions_by_level(&self, _: Level) -> Result<u32> {
+            Ok(0)
+        }
+
+        async fn count_scans_survived(
+            &self,
+            _: Level,
+            _: BlockNumber,
+            _: BlockNumber,
+        ) -> Result<u32> {
+            Ok(0)
+        }
+    }
+
+    #[async_trait]
+    impl MarketStore for FixedStore {
+        async fn save_round(&self, _: &Round) -> Result<()> {
+            Ok(())
+        }
+
+        async fn record_bet(&self, _: &Bet) -> Result<()> {
+            Ok(())
+        }
+
+        async fn resolve_round(&self, _: &str, _: &RoundSettlement) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_active_rounds(&self, _: u32) -> Result<Vec<Round>> {
+            Ok(vec![])
+        }
+
+        async fn get_round_by_id(&self, _: &str) -> Result<Option<Round>> {
+            Ok(None)
+        }
+
+        async fn get_bets_for_round(&self, _: &str) -> Result<Vec<Bet>> {
+            Ok(vec![])
+        }
+
+        async fn get_user_bets(&self, _: &EthAddress, _: u32) -> Result<Vec<Bet>> {
+            Ok(vec![])
+        }
+
+        async fn mark_bet_claimed(&self, _: &str, _: &EthAddress, _: &TokenAmount) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl StatsStore for FixedStore {
+        async fn get_global_stats(&self) -> Result<GlobalStats> {
+            Err(InfraError::NotFound.into())
+        }
+
+        async fn get_level_stats(&self, _: Level) -> Result<LevelStats> {
+            Err(InfraError::NotFound.into())
+        }
+
+        async fn update_level_stats(&self, _: Level, _: LevelStatsDelta) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_all_level_stats(&self) -> Result<Vec<LevelStats>> {
+            Ok(vec![])
+        }
+
+        async fn refresh_global_stats(&self) -> Result<GlobalStats> {
+            Err(InfraError::NotFound.into())
+        }
+
+        async fn update_global_stats(&self, _: GlobalStatsDelta) -> Result<()> {
+            Ok(())
+        }
+
+        async fn recompute_level_stats(&self) -> Result<Vec<LevelStats>> {
+            Ok(vec![])
+        }
+
+        async fn get_recent_death_counts(
+            &self,
+            _: DateTime<Utc>,
+        ) -> Result<Vec<(Level, DateTime<Utc>, u32)>> {
+            Ok(vec![])
+        }
+
+        async fn get_survival_stats(&self) -> Result<Vec<LevelSurvival>> {
+            Ok(vec![])
+        }
+
+        async fn get_exit_streak_distribution(&self) -> Result<Vec<ExitStreakCount>> {
+            Ok(vec![])
+        }
+
+        async fn record_level_scans(&self, _: &[LevelScanStats]) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_level_history(
+            &self,
+            _: Level,
+            _: DateTime<Utc>,
+            _: DateTime<Utc>,
+            _: HistoryBucket,
+        ) -> Result<Vec<LevelHistoryPoint>> {
+            Ok(vec![])
+        }
+    }
+
+    #[async_trait]
+    impl HolderStore for FixedStore {
+        async fn get_holder(&self, _: &EthAddress) -> Result<Option<HolderBalance>> {
+            Ok(None)
+        }
+
+        async fn save_holder(&self, _: &HolderBalance) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_top_holders(&self, _: HolderSort, _: u32) -> Result<Vec<HolderBalance>> {
+            Ok(vec![])
+        }
+
+        async fn count_holders(&self) -> Result<u64> {
+            Ok(0)
+        }
+
+        async fn get_holders_to_reconcile(&self, _: u32) -> Result<Vec<HolderBalance>> {
+            Ok(vec![])
+        }
+
+        async fn reconcile_holder(
+            &self,
+            _: &HolderBalance,
+            _: U256,
+            _: DateTime<Utc>,
+        ) -> Result<bool> {
+            Ok(false)
+        }
+    }
+
+    fn app() -> (axum::Router, Arc<FixedStore>) {
+        let store = Arc::new(FixedStore::default());
+        let settings = LeaderboardSettings {
+            default_limit: 2,
+            ..LeaderboardSettings::default()
+        };
+        let refresher = Arc::new(LeaderboardRefresher::new(
+            Arc::clone(&store),
+            Arc::new(MemoryCache::new()),
+            &settings,
+        ));
+        let state = ApiState::new(
+            Arc::clone(&store),
+            refresher,
+            &settings,
+            &TokenFlowSettings::default(),
+        );
+        (router(state), store)
+    }
+
+    async fn get(app: &axum::Router, uri: &str) -> (StatusCode, serde_json::Value) {
+        let response = app
+            .clone()
+            .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn returns_ranked_entries() {
+        let (app, _) = app();
+
+        let (status, body) = get(&app, "/api/v1/leaderboard/ghost_streak?limit=3").await;
+
+        assert_eq!(status, StatusCode::OK);
+        let response: LeaderboardResponse = serde_json::from_value(body).unwrap();
+        assert_eq!(response.leaderboard, LeaderboardType::GhostStreak);
+        let ranks: Vec<_> = response.entries.iter().map(|e| e.rank).collect();
+        assert_eq!(ranks, [1, 2, 3]);
+        assert_eq!(response.entries[0].score.to_string(), "42");
+    }
+
+    #[tokio::test]
+    async fn applies_default_limit() {
+        let (app, _) = app();
+
+        let (status, body) = get(&app, "/api/v1/leaderboard/ghost_streak").await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["entries"].as_array().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn repeated_requests_hit_cache() {
+        let (app, store) = app();
+
+        get(&app, "/api/v1/leaderboard/total_extracted").await;
+        get(&app, "/api/v1/leaderboard/total_extracted?limit=1").await;
+
+        assert_eq!(store.queries.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn rejects_unknown_type() {
+        let (app, _) = app();
+
+        let (status, body) = get(&app, "/api/v1/leaderboard/richest").await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"]["code"], "BAD_REQUEST");
+    }
+
+    #[tokio::test]
+    async fn rejects_zero_limit() {
+        let (app, _) = app();
+
+        let (status, _) = get(&app, "/api/v1/leaderboard/ghost_streak?limit=0").await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+}
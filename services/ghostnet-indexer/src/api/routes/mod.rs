@@ -0,0 +1,11 @@
+//! Route handlers, one module per resource.
+
+pub mod chain;
+pub mod events;
+pub mod holders;
+pub mod leaderboards;
+pub mod positions;
+pub mod rounds;
+pub mod scans;
+pub mod stats;
+pub mod users;
@@ -0,0 +1,1003 @@
+//! Position routes.
+
+use axum::Json;
+use axum::extract::{Path, Query, State};
+use serde::{Deserialize, Serialize};
+
+use chrono::{DateTime, Utc};
+
+use crate::api::ApiState;
+use crate::error::ApiError;
+use crate::ports::{DeathStore, PositionStore};
+use crate::types::entities::{
+    Boost, CascadeEarnings, HistoryCursor, Page, Position, PositionCursor, PositionFilter,
+    PositionHistoryEntry,
+};
+use crate::types::enums::Level;
+use crate::types::primitives::{EthAddress, GhostStreak, TokenAmount};
+
+/// Positions returned when a listing request has no `limit`.
+pub const DEFAULT_POSITIONS_LIMIT: u32 = 50;
+
+/// Most positions returned by a single listing request.
+pub const MAX_POSITIONS_LIMIT: u32 = 200;
+
+/// History entries returned when a request has no `limit`.
+pub const DEFAULT_HISTORY_LIMIT: u32 = 50;
+
+/// Most history entries returned by a single request.
+pub const MAX_HISTORY_LIMIT: u32 = 500;
+
+/// Query parameters for `GET /positions`.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct PositionsQuery {
+    /// Only positions at this level (e.g. `BlackIce`).
+    pub level: Option<Level>,
+    /// Only positions staking at least this many tokens.
+    pub min_stake: Option<TokenAmount>,
+    /// Only positions staking at most this many tokens.
+    pub max_stake: Option<TokenAmount>,
+    /// Only positions with at least this ghost streak.
+    pub min_streak: Option<u32>,
+    /// Only positions that are alive and not extracted.
+    #[serde(default)]
+    pub active: bool,
+    /// Only positions entered after this time (RFC 3339).
+    pub created_after: Option<DateTime<Utc>>,
+    /// Number of positions to return (defaults to [`DEFAULT_POSITIONS_LIMIT`],
+    /// capped at [`MAX_POSITIONS_LIMIT`]).
+    pub limit: Option<u32>,
+    /// Only return positions after this cursor (a previous `next_cursor`).
+    pub cursor: Option<String>,
+}
+
+/// Response body for `GET /positions`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PositionsResponse {
+    /// Matching positions of all users, newest first.
+    pub positions: Vec<Position>,
+    /// Cursor for the next page, `null` once the listing is exhausted.
+    pub next_cursor: Option<String>,
+}
+
+impl From<Page<Position>> for PositionsResponse {
+    fn from(page: Page<Position>) -> Self {
+        Self {
+            positions: page.items,
+            next_cursor: page.next_cursor,
+        }
+    }
+}
+
+/// `GET /positions?level=&min_stake=&max_stake=&min_streak=&active=&created_after=&limit=&cursor=`
+///
+/// Only the unfiltered first page is cached: it is what dashboards poll,
+/// and any other page would be invalidated by the same position changes.
+///
+/// # Errors
+///
+/// Returns `400` for a malformed `cursor`, a `min_stake` above `max_stake`,
+/// an out of range `min_streak`, or a zero `limit`.
+pub async fn list_positions<S: PositionStore>(
+    State(state): State<ApiState<S>>,
+    Query(query): Query<PositionsQuery>,
+) -> Result<Json<PositionsResponse>, ApiError> {
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_POSITIONS_LIMIT)
+        .min(MAX_POSITIONS_LIMIT);
+    if limit == 0 {
+        return Err(ApiError::BadRequest("limit must be at least 1".into()));
+    }
+    if let (Some(min), Some(max)) = (&query.min_stake, &query.max_stake)
+        && min > max
+    {
+        return Err(ApiError::BadRequest(
+            "min_stake must not exceed max_stake".into(),
+        ));
+    }
+    let min_streak = query
+        .min_streak
+        .map(|streak| {
+            i32::try_from(streak)
+                .ok()
+                .and_then(|streak| GhostStreak::new(streak).ok())
+                .ok_or_else(|| ApiError::BadRequest(format!("Invalid min_streak: {streak}")))
+        })
+        .transpose()?;
+    let after = query
+        .cursor
+        .as_deref()
+        .map(str::parse::<PositionCursor>)
+        .transpose()
+        .map_err(ApiError::BadRequest)?;
+
+    let filter = PositionFilter {
+        level: query.level,
+        min_amount: query.min_stake,
+        max_amount: query.max_stake,
+        min_streak,
+        active_only: query.active,
+        created_after: query.created_after,
+        after,
+    };
+    let cache = state
+        .positions_cache
+        .as_ref()
+        .filter(|_| filter.is_unfiltered());
+    if let Some(page) = cache.and_then(|cache| cache.get_positions_page(limit)) {
+        return Ok(Json(page.into()));
+    }
+
+    let page = state.store.query(&filter, limit).await?;
+    if let Some(cache) = cache {
+        cache.set_positions_page(limit, page.clone());
+    }
+    Ok(Json(page.into()))
+}
+
+/// A boost in effect, with the time it has left.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActiveBoost {
+    /// The boost.
+    #[serde(flatten)]
+    pub boost: Boost,
+    /// Whole seconds until the boost expires.
+    pub seconds_remaining: u64,
+}
+
+/// Response body for `GET /positions/:address`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PositionResponse {
+    /// The active position, with its raw `amount` and boost-weighted
+    /// `effective_stake`.
+    #[serde(flatten)]
+    pub position: Position,
+    /// Boosts active on the position, soonest expiry first.
+    pub boosts: Vec<ActiveBoost>,
+}
+
+/// `GET /positions/:address`
+///
+/// Returns the active position of the address with its active boosts.
+///
+/// # Errors
+///
+/// Returns `400` for a malformed `address` and `404` if the address has no
+/// active position.
+pub async fn get_position<S: PositionStore>(
+    State(state): State<ApiState<S>>,
+    Path(address): Path<String>,
+) -> Result<Json<PositionResponse>, ApiError> {
+    let address =
+        EthAddress::from_hex(&address).map_err(|e| ApiError::BadRequest(e.to_string()))?;
+    let position = state
+        .store
+        .get_active_position(&address)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("position of {address}")))?;
+
+    let now = Utc::now();
+    let boosts = match &state.boosts {
+        Some(boosts) => boosts.get_active_boosts(&address, now).await?,
+        None => Vec::new(),
+    };
+    let boosts = boosts
+        .into_iter()
+        // Boosts of an earlier position of the address are not active
+        .filter(|boost| boost.position_id == position.id)
+        .map(|boost| ActiveBoost {
+            seconds_remaining: boost.remaining(now).num_seconds().unsigned_abs(),
+            boost,
+        })
+        .collect();
+
+    Ok(Json(PositionResponse { position, boosts }))
+}
+
+/// Query parameters for `GET /positions/:address/history`.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct PositionHistoryQuery {
+    /// Number of entries to return (defaults to [`DEFAULT_HISTORY_LIMIT`],
+    /// capped at [`MAX_HISTORY_LIMIT`]).
+    pub limit: Option<u32>,
+    /// Only return entries older than this cursor (a previous `next_before`).
+    pub before: Option<String>,
+}
+
+/// Response body for `GET /positions/:address/history`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PositionHistoryResponse {
+    /// History entries across all of the address's positions, newest first.
+    pub entries: Vec<PositionHistoryEntry>,
+    /// Cursor for the next page, `null` once the history is exhausted.
+    pub next_before: Option<String>,
+}
+
+/// `GET /positions/:address/history?limit=&before=`
+///
+/// # Errors
+///
+/// Returns `400` for a malformed `address` or `before`, or a zero `limit`.
+pub async fn get_position_history<S: PositionStore>(
+    State(state): State<ApiState<S>>,
+    Path(address): Path<String>,
+    Query(query): Query<PositionHistoryQuery>,
+) -> Result<Json<PositionHistoryResponse>, ApiError> {
+    let address =
+        EthAddress::from_hex(&address).map_err(|e| ApiError::BadRequest(e.to_string()))?;
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_HISTORY_LIMIT)
+        .min(MAX_HISTORY_LIMIT);
+    if limit == 0 {
+        return Err(ApiError::BadRequest("limit must be at least 1".into()));
+    }
+    let before = query
+        .before
+        .as_deref()
+        .map(str::parse::<HistoryCursor>)
+        .transpose()
+        .map_err(ApiError::BadRequest)?;
+
+    let entries = state.store.get_history(&address, limit, before).await?;
+    // A short page is the last one
+    let next_before = (entries.len() == limit as usize)
+        .then(|| entries.last().map(|e| HistoryCursor::of(e).to_string()))
+        .flatten();
+
+    Ok(Json(PositionHistoryResponse {
+        entries,
+        next_before,
+    }))
+}
+
+/// Query parameters for `GET /positions/:address/cascades`.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct CascadeEarningsQuery {
+    /// Number of scans to return (defaults to [`DEFAULT_HISTORY_LIMIT`],
+    /// capped at [`MAX_HISTORY_LIMIT`]).
+    pub limit: Option<u32>,
+}
+
+/// `GET /positions/:address/cascades?limit=`
+///
+/// Returns the all-time cascade earnings of the address and its earnings per
+/// scan, newest first.
+///
+/// # Errors
+///
+/// Returns `400` for a malformed `address` or a zero `limit`.
+pub async fn get_cascade_earnings<S: DeathStore>(
+    State(state): State<ApiState<S>>,
+    Path(address): Path<String>,
+    Query(query): Query<CascadeEarningsQuery>,
+) -> Result<Json<CascadeEarnings>, ApiError> {
+    let address =
+        EthAddress::from_hex(&address).map_err(|e| ApiError::BadRequest(e.to_string()))?;
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_HISTORY_LIMIT)
+        .min(MAX_HISTORY_LIMIT);
+    if limit == 0 {
+        return Err(ApiError::BadRequest("limit must be at least 1".into()));
+    }
+
+    let earnings = state.store.get_cascade_earnings(&address, limit).await?;
+    Ok(Json(earnings))
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use alloy::primitives::U256;
+    use async_trait::async_trait;
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use chrono::{DateTime, TimeZone, Utc};
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::api::{chains_router, router};
+    use crate::config::{LeaderboardSettings, TokenFlowSettings};
+    use crate::error::{InfraError, Result};
+    use crate::indexer::LeaderboardRefresher;
+    use crate::ports::{
+        BoostStore, DeathStore, HolderStore, LeaderboardStore, MarketStore, ScanStore, StatsStore,
+        TokenFlowStore,
+    };
+    use crate::store::MemoryCache;
+    use crate::types::entities::{
+        AddressFlows, Bet, BurnRate, CascadeShare, Death, EffectiveStakeChange, ExitStreakCount,
+        GlobalStats, GlobalStatsDelta, HistoryBucket, HolderBalance, HolderSort, LeaderboardEntry,
+        LapsedBoost, LevelHistoryPoint, LevelScanStats, LevelStats, LevelStatsDelta, LevelSurvival,
+        OutboxEvent, Page, Position, PositionAction, PositionFilter, Round, RoundSettlement, Scan,
+        ScanCascadeEarnings, ScanFinalizationData, TokenFlowDelta, TokenTransfer,
+    };
+    use crate::types::enums::{BoostType, LeaderboardType, Level};
+    use crate::types::primitives::{BlockNumber, GhostStreak, TokenAmount};
+
+    const USER: EthAddress = EthAddress::new([0xaa; 20]);
+
+    /// Id of the active position of `USER`.
+    const POSITION_ID: Uuid = Uuid::from_u128(1);
+
+    fn position(id: Uuid, user_address: EthAddress) -> Position {
+        Position {
+            id,
+            user_address,
+            level: Level::BlackIce,
+            amount: TokenAmount::parse("600").unwrap(),
+            effective_stake: TokenAmount::parse("720").unwrap(),
+            reward_debt: TokenAmount::zero(),
+            entry_timestamp: Utc.timestamp_opt(1_700_000_000, 0).unwrap(),
+            last_add_timestamp: None,
+            ghost_streak: GhostStreak::new(4).unwrap(),
+            is_alive: true,
+            is_extracted: false,
+            exit_reason: None,
+            exit_timestamp: None,
+            survival_seconds: None,
+            scans_survived: None,
+            extracted_amount: None,
+            extracted_rewards: None,
+            created_at_block: BlockNumber::new(100),
+            updated_at: Utc.timestamp_opt(1_700_000_000, 0).unwrap(),
+        }
+    }
+
+    /// Store with five history entries for `USER`, one per block, oldest
+    /// first. Position listings return two positions and record what was
+    /// asked for.
+    #[derive(Debug)]
+    struct FixedStore {
+        history: Vec<PositionHistoryEntry>,
+        queries: std::sync::Mutex<Vec<(PositionFilter, u32)>>,
+    }
+
+    impl Default for FixedStore {
+        fn default() -> Self {
+            let position_id = Uuid::new_v4();
+            let history = (0..5)
+                .map(|i| PositionHistoryEntry {
+                    id: Uuid::now_v7(),
+                    position_id,
+                    user_address: USER,
+                    action: if i == 0 {
+                        PositionAction::JackedIn
+                    } else {
+                        PositionAction::StakeAdded
+                    },
+                    amount_change: TokenAmount::parse("10").unwrap(),
+                    new_total: TokenAmount::parse(&format!("{}", (i + 1) * 10)).unwrap(),
+                    level: Level::Subnet,
+                    ghost_streak: GhostStreak::ZERO,
+                    block_number: BlockNumber::new(100 + i),
+                    timestamp: Utc
+                        .timestamp_opt(1_700_000_000 + i.cast_signed(), 0)
+                        .unwrap(),
+                })
+                .collect();
+            Self {
+                history,
+                queries: std::sync::Mutex::default(),
+            }
+        }
+    }
+
+    impl FixedStore {
+        fn queries(&self) -> Vec<(PositionFilter, u32)> {
+            self.queries.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait]
+    impl PositionStore for FixedStore {
+        async fn get_active_position(&self, address: &EthAddress) -> Result<Option<Position>> {
+            Ok((*address == USER).then(|| position(POSITION_ID, USER)))
+        }
+
+        async fn save_position(&self, _: &Position) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_at_risk_positions(&self, _: Level, _: u32) -> Result<Vec<Position>> {
+            Ok(vec![])
+        }
+
+        async fn append_history(&self, _: &PositionHistoryEntry) -> Result<()> {
+            Ok(())
+        }
+
+        async fn save_position_with_history(
+            &self,
+            _: &Position,
+            _: &PositionHistoryEntry,
+            _: &[OutboxEvent],
+        ) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_history(
+            &self,
+            address: &EthAddress,
+            limit: u32,
+            before: Option<HistoryCursor>,
+        ) -> Result<Vec<PositionHistoryEntry>> {
+            Ok(self
+                .history
+                .iter()
+                .rev()
+                .filter(|e| e.user_address == *address)
+                .filter(|e| before.is_none_or(|c| (e.timestamp, e.id) < (c.timestamp, c.id)))
+                .take(limit as usize)
+                .cloned()
+                .collect())
+        }
+
+        async fn get_position_by_id(&self, _: &Uuid) -> Result<Option<Position>> {
+            Ok(None)
+        }
+
+        async fn get_positions_by_level(&self, _: Level) -> Result<Vec<Position>> {
+            Ok(vec![])
+        }
+
+        async fn query(&self, filter: &PositionFilter, limit: u32) -> Result<Page<Position>> {
+            self.queries.lock().unwrap().push((filter.clone(), limit));
+            let items: Vec<_> = [0xaa, 0xbb]
+                .into_iter()
+                .map(|byte| position(Uuid::new_v4(), EthAddress::new([byte; 20])))
+                .collect();
+            let next_cursor = items.last().map(|p| PositionCursor::of(p).to_string());
+            Ok(Page { items, next_cursor })
+        }
+
+        async fn count_positions_by_level(&self, _: Level) -> Result<u32> {
+            Ok(0)
+        }
+
+        async fn count_scans_survived(
+            &self,
+            _: Level,
+            _: BlockNumber,
+            _: BlockNumber,
+        ) -> Result<u32> {
+            Ok(0)
+        }
+    }
+
+    #[async_trait]
+    impl MarketStore for FixedStore {
+        async fn save_round(&self, _: &Round) -> Result<()> {
+            Ok(())
+        }
+
+        async fn record_bet(&self, _: &Bet) -> Result<()> {
+            Ok(())
+        }
+
+        async fn resolve_round(&self, _: &str, _: &RoundSettlement) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_active_rounds(&self, _: u32) -> Result<Vec<Round>> {
+            Ok(vec![])
+        }
+
+        async fn get_round_by_id(&self, _: &str) -> Result<Option<Round>> {
+            Ok(None)
+        }
+
+        async fn get_bets_for_round(&self, _: &str) -> Result<Vec<Bet>> {
+            Ok(vec![])
+        }
+
+        async fn get_user_bets(&self, _: &EthAddress, _: u32) -> Result<Vec<Bet>> {
+            Ok(vec![])
+        }
+
+        async fn mark_bet_claimed(&self, _: &str, _: &EthAddress, _: &TokenAmount) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl StatsStore for FixedStore {
+        async fn get_global_stats(&self) -> Result<GlobalStats> {
+            Err(InfraError::NotFound.into())
+        }
+
+        async fn get_level_stats(&self, _: Level) -> Result<LevelStats> {
+            Err(InfraError::NotFound.into())
+        }
+
+        async fn update_level_stats(&self, _: Level, _: LevelStatsDelta) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_all_level_stats(&self) -> Result<Vec<LevelStats>> {
+            Ok(vec![])
+        }
+
+        async fn refresh_global_stats(&self) -> Result<GlobalStats> {
+            Err(InfraError::NotFound.into())
+        }
+
+        async fn update_global_stats(&self, _: GlobalStatsDelta) -> Result<()> {
+            Ok(())
+        }
+
+        async fn recompute_level_stats(&self) -> Result<Vec<LevelStats>> {
+            Ok(vec![])
+        }
+
+        async fn get_recent_death_counts(
+            &self,
+            _: DateTime<Utc>,
+        ) -> Result<Vec<(Level, DateTime<Utc>, u32)>> {
+            Ok(vec![])
+        }
+
+        async fn get_survival_stats(&self) -> Result<Vec<LevelSurvival>> {
+            Ok(vec![])
+        }
+
+        async fn get_exit_streak_distribution(&self) -> Result<Vec<ExitStreakCount>> {
+            Ok(vec![])
+        }
+
+        async fn record_level_scans(&self, _: &[LevelScanStats]) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_level_history(
+            &self,
+            _: Level,
+            _: DateTime<Utc>,
+            _: DateTime<Utc>,
+            _: HistoryBucket,
+        ) -> Result<Vec<LevelHistoryPoint>> {
+            Ok(vec![])
+        }
+    }
+
+    #[async_trait]
+    impl LeaderboardStore for FixedStore {
+        async fn get_leaderboard(
+            &self,
+            _: LeaderboardType,
+            _: u32,
+        ) -> Result<Vec<LeaderboardEntry>> {
+            Ok(vec![])
+        }
+    }
+
+    #[async_trait]
+    impl TokenFlowStore for FixedStore {
+        async fn record_transfer(&self, _: &TokenTransfer) -> Result<()> {
+            Ok(())
+        }
+
+        async fn record_token_flows(&self, _: DateTime<Utc>, _: &TokenFlowDelta) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_burn_rate(&self, _: Duration) -> Result<BurnRate> {
+            Err(InfraError::NotFound.into())
+        }
+
+        async fn get_address_flows(&self, _: &EthAddress, _: Duration) -> Result<AddressFlows> {
+            Err(InfraError::NotFound.into())
+        }
+    }
+
+    #[async_trait]
+    impl ScanStore for FixedStore {
+        async fn save_scan(&self, _: &Scan) -> Result<()> {
+            Ok(())
+        }
+
+        async fn finalize_scan(&self, _: &str, _: ScanFinalizationData) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_recent_scans(&self, _: Level, _: u32) -> Result<Vec<Scan>> {
+            Ok(vec![])
+        }
+
+        async fn get_scan_by_id(&self, _: &str) -> Result<Option<Scan>> {
+            Ok(None)
+        }
+
+        async fn get_pending_scans(&self) -> Result<Vec<Scan>> {
+            Ok(vec![])
+        }
+
+        async fn link_deaths_to_scan(&self, _: &str, _: &[Uuid]) -> Result<u64> {
+            Ok(0)
+        }
+    }
+
+    #[async_trait]
+    impl DeathStore for FixedStore {
+        async fn record_deaths(&self, _: &[Death]) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_deaths_for_scan(&self, _: &str) -> Result<Vec<Death>> {
+            Ok(vec![])
+        }
+
+        async fn get_user_deaths(&self, _: &EthAddress, _: u32) -> Result<Vec<Death>> {
+            Ok(vec![])
+        }
+
+        async fn count_deaths_by_level(&self, _: Level) -> Result<u64> {
+            Ok(0)
+        }
+
+        async fn get_recent_deaths(&self, _: u32) -> Result<Vec<Death>> {
+            Ok(vec![])
+        }
+
+        async fn record_cascade_shares(&self, _: &[CascadeShare]) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_cascade_earnings(
+            &self,
+            address: &EthAddress,
+            limit: u32,
+        ) -> Result<CascadeEarnings> {
+            if *address != USER {
+                return Ok(CascadeEarnings {
+                    total: TokenAmount::zero(),
+                    scans: vec![],
+                });
+            }
+            let scans = self.history[1..]
+                .iter()
+                .rev()
+                .take(limit as usize)
+                .map(|e| ScanCascadeEarnings {
+                    scan_id: Some(e.block_number.to_string()),
+                    source_level: Level::Darknet,
+                    amount: TokenAmount::parse("2.5").unwrap(),
+                    block_number: e.block_number,
+                    timestamp: e.timestamp,
+                })
+                .collect();
+            Ok(CascadeEarnings {
+                total: TokenAmount::parse("10").unwrap(),
+                scans,
+            })
+        }
+    }
+
+    #[async_trait]
+    impl HolderStore for FixedStore {
+        async fn get_holder(&self, _: &EthAddress) -> Result<Option<HolderBalance>> {
+            Ok(None)
+        }
+
+        async fn save_holder(&self, _: &HolderBalance) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_top_holders(&self, _: HolderSort, _: u32) -> Result<Vec<HolderBalance>> {
+            Ok(vec![])
+        }
+
+        async fn count_holders(&self) -> Result<u64> {
+            Ok(0)
+        }
+
+        async fn get_holders_to_reconcile(&self, _: u32) -> Result<Vec<HolderBalance>> {
+            Ok(vec![])
+        }
+
+        async fn reconcile_holder(
+            &self,
+            _: &HolderBalance,
+            _: U256,
+            _: DateTime<Utc>,
+        ) -> Result<bool> {
+            Ok(false)
+        }
+    }
+
+    /// Boosts of `USER`: one an hour from now on the active position, and
+    /// one on an earlier position.
+    #[derive(Debug)]
+    struct FixedBoosts;
+
+    #[async_trait]
+    impl BoostStore for FixedBoosts {
+        async fn save_boost(&self, _: &Boost) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_active_boosts(
+            &self,
+            address: &EthAddress,
+            now: DateTime<Utc>,
+        ) -> Result<Vec<Boost>> {
+            let boost = |position_id| Boost {
+                id: Uuid::new_v4(),
+                position_id,
+                user_address: USER,
+                boost_type: BoostType::DeathReduction,
+                value_bps: 1500,
+                expiry: now + chrono::TimeDelta::hours(1),
+                block_number: BlockNumber::new(100),
+                log_index: 0,
+                created_at: now,
+                expired_at: None,
+            };
+            Ok(if *address == USER {
+                vec![boost(POSITION_ID), boost(Uuid::from_u128(2))]
+            } else {
+                vec![]
+            })
+        }
+
+        async fn get_expiring_boosts(
+            &self,
+            _: DateTime<Utc>,
+            _: DateTime<Utc>,
+            _: u32,
+        ) -> Result<Vec<Boost>> {
+            Ok(vec![])
+        }
+
+        async fn get_lapsed_boosts(&self, _: DateTime<Utc>, _: u32) -> Result<Vec<LapsedBoost>> {
+            Ok(vec![])
+        }
+
+        async fn expire_boosts(&self, _: &[(Uuid, DateTime<Utc>)]) -> Result<u64> {
+            Ok(0)
+        }
+
+        async fn reweigh_positions(
+            &self,
+            _: &[Uuid],
+            _: DateTime<Utc>,
+        ) -> Result<Vec<EffectiveStakeChange>> {
+            Ok(vec![])
+        }
+    }
+
+    fn app() -> (axum::Router, Arc<FixedStore>) {
+        let store = Arc::new(FixedStore::default());
+        (router(state(&store)), store)
+    }
+
+    fn state(store: &Arc<FixedStore>) -> ApiState<FixedStore> {
+        let leaderboard = LeaderboardSettings::default();
+        let refresher = Arc::new(LeaderboardRefresher::new(
+            Arc::clone(store),
+            Arc::new(MemoryCache::new()),
+            &leaderboard,
+        ));
+        ApiState::new(
+            Arc::clone(store),
+            refresher,
+            &leaderboard,
+            &TokenFlowSettings::default(),
+        )
+        .with_positions_cache(Arc::new(MemoryCache::new()))
+        .with_boosts(Arc::new(FixedBoosts))
+    }
+
+    async fn get(app: &axum::Router, uri: &str) -> (StatusCode, serde_json::Value) {
+        let response = app
+            .clone()
+            .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn returns_active_position_with_its_boosts() {
+        let (app, _) = app();
+
+        let (status, body) = get(&app, &format!("/api/v1/positions/{USER}")).await;
+
+        assert_eq!(status, StatusCode::OK);
+        // Both the raw and the boost-weighted stake are shown
+        assert_eq!(body["amount"], "600");
+        assert_eq!(body["effective_stake"], "720");
+        let response: PositionResponse = serde_json::from_value(body).unwrap();
+        assert_eq!(response.position.id, POSITION_ID);
+        assert_eq!(response.boosts.len(), 1);
+        assert_eq!(response.boosts[0].boost.position_id, POSITION_ID);
+        let remaining = response.boosts[0].seconds_remaining;
+        assert!((3590..=3600).contains(&remaining), "{remaining}");
+    }
+
+    #[tokio::test]
+    async fn serves_each_chain_from_its_own_store() {
+        let testnet = Arc::new(FixedStore::default());
+        let mainnet = Arc::new(FixedStore::default());
+        let chains = vec![(6343, state(&testnet)), (4326, state(&mainnet))];
+        let app = chains_router(6343, chains);
+
+        let (status, _) = get(&app, "/api/v1/chains/4326/positions?limit=3").await;
+        assert_eq!(status, StatusCode::OK);
+        let (status, _) = get(&app, "/api/v1/chains/6343/positions?limit=4").await;
+        assert_eq!(status, StatusCode::OK);
+        // The primary chain keeps the routes without a chain
+        let (status, _) = get(&app, "/api/v1/positions?limit=5").await;
+        assert_eq!(status, StatusCode::OK);
+
+        let limits = |store: &FixedStore| -> Vec<u32> {
+            store.queries().iter().map(|&(_, limit)| limit).collect()
+        };
+        assert_eq!(limits(&mainnet), [3]);
+        assert_eq!(limits(&testnet), [4, 5]);
+
+        let response = app
+            .oneshot(
+                Request::get("/api/v1/chains/1/positions")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn address_without_active_position_is_not_found() {
+        let (app, _) = app();
+        let other = EthAddress::new([0x11; 20]);
+
+        let (status, _) = get(&app, &format!("/api/v1/positions/{other}")).await;
+
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn pages_history_newest_first() {
+        let (app, store) = app();
+        let uri = format!("/api/v1/positions/{USER}/history?limit=2");
+
+        let mut blocks = Vec::new();
+        let mut next = Some(uri.clone());
+        while let Some(uri) = next {
+            let (status, body) = get(&app, &uri).await;
+            assert_eq!(status, StatusCode::OK);
+            let page: PositionHistoryResponse = serde_json::from_value(body).unwrap();
+            assert!(page.entries.len() <= 2);
+            blocks.extend(page.entries.iter().map(|e| e.block_number.get()));
+            next = page
+                .next_before
+                .map(|cursor| format!("/api/v1/positions/{USER}/history?limit=2&before={cursor}"));
+        }
+
+        assert_eq!(blocks, [104, 103, 102, 101, 100]);
+        assert_eq!(blocks.len(), store.history.len());
+    }
+
+    #[tokio::test]
+    async fn unknown_address_has_empty_history() {
+        let (app, _) = app();
+        let other = EthAddress::new([0x11; 20]);
+
+        let (status, body) = get(&app, &format!("/api/v1/positions/{other}/history")).await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["entries"], serde_json::json!([]));
+        assert_eq!(body["next_before"], serde_json::Value::Null);
+    }
+
+    #[tokio::test]
+    async fn returns_cascade_earnings_per_scan() {
+        let (app, _) = app();
+
+        let (status, body) = get(&app, &format!("/api/v1/positions/{USER}/cascades?limit=3")).await;
+
+        assert_eq!(status, StatusCode::OK);
+        let earnings: CascadeEarnings = serde_json::from_value(body).unwrap();
+        assert_eq!(earnings.total, TokenAmount::parse("10").unwrap());
+        let blocks: Vec<_> = earnings.scans.iter().map(|s| s.block_number.get()).collect();
+        assert_eq!(blocks, [104, 103, 102]);
+    }
+
+    #[tokio::test]
+    async fn lists_positions_matching_filters() {
+        let (app, store) = app();
+        let uri = "/api/v1/positions?level=BlackIce&min_stake=500&max_stake=1000&min_streak=3\
+                   &active=true&created_after=2023-11-01T00:00:00Z&limit=2";
+
+        let (status, body) = get(&app, uri).await;
+
+        assert_eq!(status, StatusCode::OK);
+        let page: PositionsResponse = serde_json::from_value(body).unwrap();
+        assert_eq!(page.positions.len(), 2);
+        let last = page.positions.last().unwrap();
+        assert_eq!(
+            page.next_cursor,
+            Some(PositionCursor::of(last).to_string())
+        );
+
+        let expected = PositionFilter {
+            level: Some(Level::BlackIce),
+            min_amount: Some(TokenAmount::parse("500").unwrap()),
+            max_amount: Some(TokenAmount::parse("1000").unwrap()),
+            min_streak: Some(GhostStreak::new(3).unwrap()),
+            active_only: true,
+            created_after: Some(Utc.with_ymd_and_hms(2023, 11, 1, 0, 0, 0).unwrap()),
+            after: None,
+        };
+        assert_eq!(store.queries(), [(expected, 2)]);
+    }
+
+    #[tokio::test]
+    async fn passes_cursor_and_caps_limit() {
+        let (app, store) = app();
+        let cursor = PositionCursor {
+            entry_timestamp: Utc.timestamp_millis_opt(1_700_000_000_123).unwrap(),
+            id: Uuid::new_v4(),
+        };
+
+        let uri = format!("/api/v1/positions?cursor={cursor}&limit=10000");
+        let (status, _) = get(&app, &uri).await;
+
+        assert_eq!(status, StatusCode::OK);
+        let queries = store.queries();
+        assert_eq!(queries[0].0.after, Some(cursor));
+        assert_eq!(queries[0].1, MAX_POSITIONS_LIMIT);
+    }
+
+    #[tokio::test]
+    async fn caches_only_the_unfiltered_first_page() {
+        let (app, store) = app();
+
+        for _ in 0..3 {
+            let (status, _) = get(&app, "/api/v1/positions").await;
+            assert_eq!(status, StatusCode::OK);
+        }
+        assert_eq!(store.queries().len(), 1);
+
+        for _ in 0..2 {
+            let (status, _) = get(&app, "/api/v1/positions?level=Vault").await;
+            assert_eq!(status, StatusCode::OK);
+        }
+        assert_eq!(store.queries().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn rejects_malformed_parameters() {
+        let (app, _) = app();
+
+        for uri in [
+            "/api/v1/positions/0x1234".to_string(),
+            "/api/v1/positions/0x1234/history".to_string(),
+            format!("/api/v1/positions/{USER}/history?before=yesterday"),
+            format!("/api/v1/positions/{USER}/history?limit=0"),
+            "/api/v1/positions/0x1234/cascades".to_string(),
+            format!("/api/v1/positions/{USER}/cascades?limit=0"),
+            "/api/v1/positions?cursor=yesterday".to_string(),
+            "/api/v1/positions?limit=0".to_string(),
+            "/api/v1/positions?min_stake=10&max_stake=5".to_string(),
+        ] {
+            let (status, _) = get(&app, &uri).await;
+            assert_eq!(status, StatusCode::BAD_REQUEST, "{uri}");
+        }
+    }
+}
@@ -0,0 +1,236 @@
+//! `DeadPool` round routes.
+
+use alloy::primitives::U256;
+use axum::Json;
+use axum::extract::{Path, State};
+use serde::{Deserialize, Serialize};
+
+use crate::api::ApiState;
+use crate::error::ApiError;
+use crate::ports::MarketStore;
+use crate::types::entities::OddsPoint;
+
+/// Response body for `GET /rounds/:id/odds-history`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OddsHistoryResponse {
+    /// On-chain round ID.
+    pub round_id: String,
+    /// Whether the round was resolved.
+    pub is_resolved: bool,
+    /// Outcome (true = OVER won), once resolved.
+    pub outcome: Option<bool>,
+    /// Multiple of their stake the winners were paid, in basis points.
+    pub payout_multiple_bps: Option<u64>,
+    /// Odds after each bet, oldest first.
+    pub points: Vec<OddsPoint>,
+}
+
+/// `GET /rounds/:id/odds-history`
+///
+/// `id` is the on-chain round ID in decimal. The odds are replayed from the
+/// round's bets in chain order, starting from empty pools.
+///
+/// # Errors
+///
+/// Returns `400` for a malformed `id` and `404` for an unknown round.
+pub async fn get_odds_history<S: MarketStore>(
+    State(state): State<ApiState<S>>,
+    Path(id): Path<String>,
+) -> Result<Json<OddsHistoryResponse>, ApiError> {
+    // Normalize so that e.g. "007" finds round "7"
+    let round_id = U256::from_str_radix(&id, 10)
+        .map_err(|e| ApiError::BadRequest(format!("invalid round id {id:?}: {e}")))?
+        .to_string();
+
+    let round = state
+        .store
+        .get_round_by_id(&round_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("round {round_id}")))?;
+    let bets = state.store.get_bets_for_round(&round_id).await?;
+
+    Ok(Json(OddsHistoryResponse {
+        round_id: round.round_id,
+        is_resolved: round.is_resolved,
+        outcome: round.outcome,
+        payout_multiple_bps: round.payout_multiple_bps,
+        points: OddsPoint::history(&bets),
+    }))
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::sync::Arc;
+
+    use async_trait::async_trait;
+    use chrono::{TimeZone, Utc};
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::config::{LeaderboardSettings, TokenFlowSettings};
+    use crate::error::Result;
+    use crate::indexer::LeaderboardRefresher;
+    use crate::ports::LeaderboardStore;
+    use crate::store::MemoryCache;
+    use crate::types::entities::{Bet, LeaderboardEntry, Pools, Round, RoundSettlement};
+    use crate::types::enums::{LeaderboardType, RoundType};
+    use crate::types::primitives::{BlockNumber, EthAddress, TokenAmount};
+
+    /// Store with round 7 and its bets.
+    #[derive(Debug)]
+    struct FixedStore {
+        round: Round,
+        bets: Vec<Bet>,
+    }
+
+    impl Default for FixedStore {
+        fn default() -> Self {
+            let round = Round {
+                id: Uuid::new_v4(),
+                round_id: "7".into(),
+                round_type: RoundType::DeathCount,
+                target_level: None,
+                line: TokenAmount::parse("10").unwrap(),
+                deadline: Utc.timestamp_opt(1_700_000_000, 0).unwrap(),
+                over_pool: TokenAmount::parse("300").unwrap(),
+                under_pool: TokenAmount::parse("100").unwrap(),
+                is_resolved: false,
+                outcome: None,
+                resolve_time: None,
+                total_burned: None,
+                payout_multiple_bps: None,
+                house_take: None,
+            };
+            let bet = |block: u64, log_index: u64, is_over: bool, amount: &str| Bet {
+                id: Uuid::new_v4(),
+                round_id: round.id,
+                user_address: EthAddress::new([0xaa; 20]),
+                amount: TokenAmount::parse(amount).unwrap(),
+                is_over,
+                block_number: BlockNumber::new(block),
+                log_index,
+                placed_at: Utc.timestamp_opt(1_699_990_000 + block.cast_signed(), 0).unwrap(),
+                pools_before: Pools::default(),
+                implied_probability_bps: None,
+                payout_multiple_bps: None,
+                is_claimed: false,
+                winnings: None,
+                claimed_at: None,
+            };
+            let bets = vec![
+                bet(10, 2, true, "100"),
+                bet(10, 5, false, "100"),
+                bet(11, 0, true, "200"),
+            ];
+            Self { round, bets }
+        }
+    }
+
+    #[async_trait]
+    impl MarketStore for FixedStore {
+        async fn save_round(&self, _: &Round) -> Result<()> {
+            Ok(())
+        }
+
+        async fn record_bet(&self, _: &Bet) -> Result<()> {
+            Ok(())
+        }
+
+        async fn resolve_round(&self, _: &str, _: &RoundSettlement) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_active_rounds(&self, _: u32) -> Result<Vec<Round>> {
+            Ok(vec![])
+        }
+
+        async fn get_round_by_id(&self, round_id: &str) -> Result<Option<Round>> {
+            Ok((round_id == self.round.round_id).then(|| self.round.clone()))
+        }
+
+        async fn get_bets_for_round(&self, round_id: &str) -> Result<Vec<Bet>> {
+            Ok(if round_id == self.round.round_id {
+                self.bets.clone()
+            } else {
+                vec![]
+            })
+        }
+
+        async fn get_user_bets(&self, _: &EthAddress, _: u32) -> Result<Vec<Bet>> {
+            Ok(vec![])
+        }
+
+        async fn mark_bet_claimed(&self, _: &str, _: &EthAddress, _: &TokenAmount) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl LeaderboardStore for FixedStore {
+        async fn get_leaderboard(
+            &self,
+            _: LeaderboardType,
+            _: u32,
+        ) -> Result<Vec<LeaderboardEntry>> {
+            Ok(vec![])
+        }
+    }
+
+    fn state() -> ApiState<FixedStore> {
+        let store = Arc::new(FixedStore::default());
+        let leaderboard = LeaderboardSettings::default();
+        let refresher = Arc::new(LeaderboardRefresher::new(
+            Arc::clone(&store),
+            Arc::new(MemoryCache::new()),
+            &leaderboard,
+        ));
+        ApiState::new(store, refresher, &leaderboard, &TokenFlowSettings::default())
+    }
+
+    #[tokio::test]
+    async fn odds_history_replays_the_bets() {
+        let Json(response) = get_odds_history(State(state()), Path("007".into()))
+            .await
+            .unwrap();
+
+        assert_eq!(response.round_id, "7");
+        let odds: Vec<_> = response
+            .points
+            .iter()
+            .map(|point| {
+                (
+                    point.block_number.value(),
+                    point.over_probability_bps,
+                    point.over_payout_multiple_bps,
+                    point.under_payout_multiple_bps,
+                )
+            })
+            .collect();
+        assert_eq!(
+            odds,
+            [
+                (10, Some(10_000), Some(9_500), None),
+                (10, Some(5_000), Some(19_000), Some(19_000)),
+                (11, Some(7_500), Some(12_666), Some(38_000)),
+            ]
+        );
+
+        let body = serde_json::to_value(&response).unwrap();
+        assert_eq!(body["points"][2]["over_pool"], "300");
+        assert_eq!(body["points"][0]["under_probability_bps"], 0);
+    }
+
+    #[tokio::test]
+    async fn unknown_and_malformed_rounds_are_rejected() {
+        let unknown = get_odds_history(State(state()), Path("8".into())).await;
+        assert!(matches!(unknown, Err(ApiError::NotFound(_))));
+
+        let malformed = get_odds_history(State(state()), Path("x".into())).await;
+        assert!(matches!(malformed, Err(ApiError::BadRequest(_))));
+    }
+}
@@ -0,0 +1,576 @@
+//! Scan routes.
+
+use alloy::primitives::U256;
+use axum::Json;
+use axum::extract::{Path, State};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use crate::api::ApiState;
+use crate::error::ApiError;
+use crate::ports::{DeathStore, ScanStore};
+use crate::types::entities::{Death, Scan, ScanPrediction};
+
+/// Response body for `GET /scans/:id`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanResponse {
+    /// The scan, including `finalization_latency_ms` (`null` until it
+    /// finalizes).
+    #[serde(flatten)]
+    pub scan: Scan,
+    /// Whether the scan was created before its `ScanExecuted` event was seen.
+    pub placeholder: bool,
+    /// Deaths linked to the scan, oldest first.
+    pub deaths: Vec<Death>,
+}
+
+/// Predicted next scan of one level.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NextScan {
+    /// The prediction, including the cadence it was derived from.
+    #[serde(flatten)]
+    pub prediction: ScanPrediction,
+    /// Seconds until `next_scan_at`, `0` once it passed.
+    pub seconds_remaining: u64,
+}
+
+/// Response body for `GET /scans/next`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NextScansResponse {
+    /// Levels with a prediction, safest first.
+    pub levels: Vec<NextScan>,
+}
+
+/// `GET /scans/next`
+///
+/// Levels without scans, and levels with too little history and no
+/// on-chain schedule, are left out.
+///
+/// # Errors
+///
+/// Returns `404` if scan prediction is not configured and `500` if the scan
+/// history cannot be read.
+pub async fn get_next_scans<S: ScanStore>(
+    State(state): State<ApiState<S>>,
+) -> Result<Json<NextScansResponse>, ApiError> {
+    let predictor = state
+        .scan_predictor
+        .as_ref()
+        .ok_or_else(|| ApiError::NotFound("scan predictions".into()))?;
+
+    let now = Utc::now();
+    let levels = predictor
+        .predict_all()
+        .await?
+        .into_iter()
+        .map(|prediction| NextScan {
+            seconds_remaining: prediction.seconds_remaining(now),
+            prediction,
+        })
+        .collect();
+
+    Ok(Json(NextScansResponse { levels }))
+}
+
+/// `GET /scans/:id`
+///
+/// `id` is the on-chain scan ID in decimal.
+///
+/// # Errors
+///
+/// Returns `400` for a malformed `id` and `404` for an unknown scan.
+pub async fn get_scan<S: ScanStore + DeathStore>(
+    State(state): State<ApiState<S>>,
+    Path(id): Path<String>,
+) -> Result<Json<ScanResponse>, ApiError> {
+    // Normalize so that e.g. "007" finds scan "7"
+    let scan_id = U256::from_str_radix(&id, 10)
+        .map_err(|e| ApiError::BadRequest(format!("invalid scan id {id:?}: {e}")))?
+        .to_string();
+
+    let scan = state
+        .store
+        .get_scan_by_id(&scan_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("scan {scan_id}")))?;
+    let deaths = state.store.get_deaths_for_scan(&scan_id).await?;
+
+    Ok(Json(ScanResponse {
+        placeholder: scan.is_placeholder(),
+        scan,
+        deaths,
+    }))
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use alloy::primitives::U256;
+    use async_trait::async_trait;
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use chrono::{DateTime, TimeZone, Utc};
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::api::router;
+    use crate::config::{LeaderboardSettings, ScanPredictionSettings, TokenFlowSettings};
+    use crate::error::{InfraError, Result};
+    use crate::indexer::{LeaderboardRefresher, ScanPredictor};
+    use crate::ports::{
+        HolderStore, LeaderboardStore, MarketStore, PositionStore, StatsStore, TokenFlowStore,
+    };
+    use crate::store::MemoryCache;
+    use crate::types::entities::{
+        AddressFlows, Bet, BurnRate, CascadeEarnings, CascadeShare, ExitStreakCount, GlobalStats,
+        GlobalStatsDelta, HistoryBucket, HistoryCursor, HolderBalance, HolderSort,
+        LeaderboardEntry, LevelHistoryPoint, LevelScanStats, LevelStats, LevelStatsDelta,
+        LevelSurvival, OutboxEvent, Page, Position, PositionFilter, PositionHistoryEntry, Round,
+        RoundSettlement, ScanFinalizationData, TokenFlowDelta, TokenTransfer,
+    };
+    use crate::types::enums::{LeaderboardType, Level};
+    use crate::types::primitives::{BlockNumber, EthAddress, TokenAmount};
+
+    /// Store with one finalized scan, one death linked to it, and the
+    /// Subnet scan eight hours before it.
+    #[derive(Debug)]
+    struct FixedStore {
+        scan: Scan,
+        death: Death,
+        previous: Scan,
+    }
+
+    impl Default for FixedStore {
+        fn default() -> Self {
+            let executed_at = Utc.timestamp_opt(1_700_000_000, 0).unwrap();
+            let scan = Scan {
+                seed: "42".into(),
+                finalized_at: Some(executed_at + chrono::Duration::seconds(90)),
+                death_count: Some(1),
+                finalization_latency_ms: Some(90_000),
+                ..Scan::placeholder("7".into(), Level::Subnet, executed_at)
+            };
+            let death = Death {
+                id: Uuid::new_v4(),
+                scan_id: Some(scan.id),
+                user_address: EthAddress::new([0xaa; 20]),
+                position_id: None,
+                amount_lost: TokenAmount::parse("100").unwrap(),
+                level: Level::Subnet,
+                ghost_streak_at_death: None,
+                created_at: executed_at,
+            };
+            let previous = Scan {
+                seed: "41".into(),
+                ..Scan::placeholder(
+                    "6".into(),
+                    Level::Subnet,
+                    executed_at - chrono::Duration::hours(8),
+                )
+            };
+            Self {
+                scan,
+                death,
+                previous,
+            }
+        }
+    }
+
+    #[async_trait]
+    impl ScanStore for FixedStore {
+        async fn save_scan(&self, _: &Scan) -> Result<()> {
+            Ok(())
+        }
+
+        async fn finalize_scan(&self, _: &str, _: ScanFinalizationData) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_recent_scans(&self, level: Level, _: u32) -> Result<Vec<Scan>> {
+            let scans = [self.scan.clone(), self.previous.clone()];
+            Ok(scans.into_iter().filter(|scan| scan.level == level).collect())
+        }
+
+        async fn get_scan_by_id(&self, scan_id: &str) -> Result<Option<Scan>> {
+            Ok((scan_id == self.scan.scan_id).then(|| self.scan.clone()))
+        }
+
+        async fn get_pending_scans(&self) -> Result<Vec<Scan>> {
+            Ok(vec![])
+        }
+
+        async fn link_deaths_to_scan(&self, _: &str, _: &[Uuid]) -> Result<u64> {
+            Ok(0)
+        }
+    }
+
+    #[async_trait]
+    impl DeathStore for FixedStore {
+        async fn record_deaths(&self, _: &[Death]) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_deaths_for_scan(&self, scan_id: &str) -> Result<Vec<Death>> {
+            Ok(if scan_id == self.scan.scan_id {
+                vec![self.death.clone()]
+            } else {
+                vec![]
+            })
+        }
+
+        async fn get_user_deaths(&self, _: &EthAddress, _: u32) -> Result<Vec<Death>> {
+            Ok(vec![])
+        }
+
+        async fn count_deaths_by_level(&self, _: Level) -> Result<u64> {
+            Ok(0)
+        }
+
+        async fn get_recent_deaths(&self, _: u32) -> Result<Vec<Death>> {
+            Ok(vec![])
+        }
+
+        async fn record_cascade_shares(&self, _: &[CascadeShare]) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_cascade_earnings(&self, _: &EthAddress, _: u32) -> Result<CascadeEarnings> {
+            Ok(CascadeEarnings {
+                total: TokenAmount::zero(),
+                scans: vec![],
+            })
+        }
+    }
+
+    #[async_trait]
+    impl LeaderboardStore for FixedStore {
+        async fn get_leaderboard(
+            &self,
+            _: LeaderboardType,
+            _: u32,
+        ) -> Result<Vec<LeaderboardEntry>> {
+            Ok(vec![])
+        }
+    }
+
+    #[async_trait]
+    impl TokenFlowStore for FixedStore {
+        async fn record_transfer(&self, _: &TokenTransfer) -> Result<()> {
+            Ok(())
+        }
+
+        async fn record_token_flows(&self, _: DateTime<Utc>, _: &TokenFlowDelta) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_burn_rate(&self, _: Duration) -> Result<BurnRate> {
+            Err(InfraError::NotFound.into())
+        }
+
+        async fn get_address_flows(&self, _: &EthAddress, _: Duration) -> Result<AddressFlows> {
+            Err(InfraError::NotFound.into())
+        }
+    }
+
+    #[async_trait]
+    impl PositionStore for FixedStore {
+        async fn get_active_position(&self, _: &EthAddress) -> Result<Option<Position>> {
+            Ok(None)
+        }
+
+        async fn save_position(&self, _: &Position) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_at_risk_positions(&self, _: Level, _: u32) -> Result<Vec<Position>> {
+            Ok(vec![])
+        }
+
+        async fn append_history(&self, _: &PositionHistoryEntry) -> Result<()> {
+            Ok(())
+        }
+
+        async fn save_position_with_history(
+            &self,
+            _: &Position,
+            _: &PositionHistoryEntry,
+            _: &[OutboxEvent],
+        ) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_history(
+            &self,
+            _: &EthAddress,
+            _: u32,
+            _: Option<HistoryCursor>,
+        ) -> Result<Vec<PositionHistoryEntry>> {
+            Ok(vec![])
+        }
+
+        async fn get_position_by_id(&self, _: &Uuid) -> Result<Option<Position>> {
+            Ok(None)
+        }
+
+        async fn get_positions_by_level(&self, _: Level) -> Result<Vec<Position>> {
+            Ok(vec![])
+        }
+
+        async fn query(&self, _: &PositionFilter, _: u32) -> Result<Page<Position>> {
+            Ok(Page {
+                items: vec![],
+                next_cursor: None,
+            })
+        }
+
+        async fn count_positions_by_level(&self, _: Level) -> Result<u32> {
+            Ok(0)
+        }
+
+        async fn count_scans_survived(
+            &self,
+            _: Level,
+            _: BlockNumber,
+            _: BlockNumber,
+        ) -> Result<u32> {
+            Ok(0)
+        }
+    }
+
+    #[async_trait]
+    impl MarketStore for FixedStore {
+        async fn save_round(&self, _: &Round) -> Result<()> {
+            Ok(())
+        }
+
+        async fn record_bet(&self, _: &Bet) -> Result<()> {
+            Ok(())
+        }
+
+        async fn resolve_round(&self, _: &str, _: &RoundSettlement) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_active_rounds(&self, _: u32) -> Result<Vec<Round>> {
+            Ok(vec![])
+        }
+
+        async fn get_round_by_id(&self, _: &str) -> Result<Option<Round>> {
+            Ok(None)
+        }
+
+        async fn get_bets_for_round(&self, _: &str) -> Result<Vec<Bet>> {
+            Ok(vec![])
+        }
+
+        async fn get_user_bets(&self, _: &EthAddress, _: u32) -> Result<Vec<Bet>> {
+            Ok(vec![])
+        }
+
+        async fn mark_bet_claimed(&self, _: &str, _: &EthAddress, _: &TokenAmount) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl StatsStore for FixedStore {
+        async fn get_global_stats(&self) -> Result<GlobalStats> {
+            Err(InfraError::NotFound.into())
+        }
+
+        async fn get_level_stats(&self, _: Level) -> Result<LevelStats> {
+            Err(InfraError::NotFound.into())
+        }
+
+        async fn update_level_stats(&self, _: Level, _: LevelStatsDelta) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_all_level_stats(&self) -> Result<Vec<LevelStats>> {
+            Ok(vec![])
+        }
+
+        async fn refresh_global_stats(&self) -> Result<GlobalStats> {
+            Err(InfraError::NotFound.into())
+        }
+
+        async fn update_global_stats(&self, _: GlobalStatsDelta) -> Result<()> {
+            Ok(())
+        }
+
+        async fn recompute_level_stats(&self) -> Result<Vec<LevelStats>> {
+            Ok(vec![])
+        }
+
+        async fn get_recent_death_counts(
+            &self,
+            _: DateTime<Utc>,
+        ) -> Result<Vec<(Level, DateTime<Utc>, u32)>> {
+            Ok(vec![])
+        }
+
+        async fn get_survival_stats(&self) -> Result<Vec<LevelSurvival>> {
+            Ok(vec![])
+        }
+
+        async fn get_exit_streak_distribution(&self) -> Result<Vec<ExitStreakCount>> {
+            Ok(vec![])
+        }
+
+        async fn record_level_scans(&self, _: &[LevelScanStats]) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_level_history(
+            &self,
+            _: Level,
+            _: DateTime<Utc>,
+            _: DateTime<Utc>,
+            _: HistoryBucket,
+        ) -> Result<Vec<LevelHistoryPoint>> {
+            Ok(vec![])
+        }
+    }
+
+    #[async_trait]
+    impl HolderStore for FixedStore {
+        async fn get_holder(&self, _: &EthAddress) -> Result<Option<HolderBalance>> {
+            Ok(None)
+        }
+
+        async fn save_holder(&self, _: &HolderBalance) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_top_holders(&self, _: HolderSort, _: u32) -> Result<Vec<HolderBalance>> {
+            Ok(vec![])
+        }
+
+        async fn count_holders(&self) -> Result<u64> {
+            Ok(0)
+        }
+
+        async fn get_holders_to_reconcile(&self, _: u32) -> Result<Vec<HolderBalance>> {
+            Ok(vec![])
+        }
+
+        async fn reconcile_holder(
+            &self,
+            _: &HolderBalance,
+            _: U256,
+            _: DateTime<Utc>,
+        ) -> Result<bool> {
+            Ok(false)
+        }
+    }
+
+    fn app() -> (axum::Router, Arc<FixedStore>) {
+        let (state, store) = state();
+        (router(state), store)
+    }
+
+    fn state() -> (ApiState<FixedStore>, Arc<FixedStore>) {
+        let store = Arc::new(FixedStore::default());
+        let leaderboard = LeaderboardSettings::default();
+        let refresher = Arc::new(LeaderboardRefresher::new(
+            Arc::clone(&store),
+            Arc::new(MemoryCache::new()),
+            &leaderboard,
+        ));
+        let state = ApiState::new(
+            Arc::clone(&store),
+            refresher,
+            &leaderboard,
+            &TokenFlowSettings::default(),
+        );
+        (state, store)
+    }
+
+    async fn get(app: &axum::Router, uri: &str) -> (StatusCode, serde_json::Value) {
+        let response = app
+            .clone()
+            .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn returns_scan_with_deaths_and_latency() {
+        let (app, store) = app();
+
+        let (status, body) = get(&app, "/api/v1/scans/007").await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["finalization_latency_ms"], 90_000);
+        assert_eq!(body["placeholder"], false);
+        let response: ScanResponse = serde_json::from_value(body).unwrap();
+        assert_eq!(response.scan, store.scan);
+        assert_eq!(response.deaths, std::slice::from_ref(&store.death));
+    }
+
+    #[tokio::test]
+    async fn unknown_scan_is_not_found() {
+        let (app, _) = app();
+
+        let (status, body) = get(&app, "/api/v1/scans/8").await;
+
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body["error"]["code"], "NOT_FOUND");
+    }
+
+    #[tokio::test]
+    #[allow(clippy::panic)]
+    async fn next_scans_are_predicted_from_history() {
+        let (state, store) = state();
+        let predictor = ScanPredictor::new(Arc::clone(&store), &ScanPredictionSettings::default());
+        let app = router(state.with_scan_predictor(Arc::new(predictor)));
+
+        let (status, body) = get(&app, "/api/v1/scans/next").await;
+
+        assert_eq!(status, StatusCode::OK);
+        let response: NextScansResponse = serde_json::from_value(body).unwrap();
+        let [next] = response.levels.as_slice() else {
+            panic!("expected one level, got {:?}", response.levels);
+        };
+        // Long past: eight hours after the fixed scan in 2023
+        assert_eq!(next.prediction.level, Level::Subnet);
+        assert_eq!(
+            next.prediction.next_scan_at,
+            store.scan.executed_at + chrono::Duration::hours(8)
+        );
+        assert_eq!(next.seconds_remaining, 0);
+        let cadence = next.prediction.cadence.as_ref().unwrap();
+        assert_eq!((cadence.intervals, cadence.median_interval_secs), (1, 28_800));
+    }
+
+    #[tokio::test]
+    async fn next_scans_need_a_predictor() {
+        let (app, _) = app();
+
+        let (status, _) = get(&app, "/api/v1/scans/next").await;
+
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn rejects_malformed_id() {
+        let (app, _) = app();
+
+        let (status, _) = get(&app, "/api/v1/scans/0x07").await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+}
@@ -0,0 +1,760 @@
+//! Aggregate statistics routes.
+
+use std::time::Duration;
+
+use axum::Json;
+use axum::extract::{Path, Query, State};
+use chrono::{DateTime, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::api::ApiState;
+use crate::error::ApiError;
+use crate::ports::{StatsStore, TokenFlowStore};
+use crate::types::entities::{
+    AddressFlows, BurnRate, ExitStreakCount, HistoryBucket, LevelHistoryPoint, LevelSurvival,
+};
+use crate::types::enums::Level;
+use crate::types::primitives::{EthAddress, TokenAmount};
+
+/// Most buckets returned by a single level history request.
+pub const MAX_HISTORY_POINTS: i64 = 1000;
+
+/// Range of a level history request without `from`.
+pub const DEFAULT_HISTORY_RANGE: TimeDelta = TimeDelta::days(1);
+
+/// Query parameters for `GET /stats/token`.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TokenStatsQuery {
+    /// Window length in seconds (defaults to `token_flows.default_window_secs`).
+    pub window_secs: Option<u64>,
+    /// Address to include inflow/outflow for.
+    pub address: Option<String>,
+}
+
+/// Response body for `GET /stats/token`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenStatsResponse {
+    /// Burn and tax totals over the window.
+    pub burn_rate: BurnRate,
+    /// Flows of the requested address, if one was given.
+    pub address_flows: Option<AddressFlowsBody>,
+}
+
+/// Address flows with their net direction.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddressFlowsBody {
+    /// Raw inflow and outflow totals.
+    #[serde(flatten)]
+    pub flows: AddressFlows,
+    /// Amount by which inflow exceeds outflow.
+    pub net_inflow: TokenAmount,
+    /// Amount by which outflow exceeds inflow.
+    pub net_outflow: TokenAmount,
+}
+
+impl From<AddressFlows> for AddressFlowsBody {
+    fn from(flows: AddressFlows) -> Self {
+        Self {
+            net_inflow: flows.net_inflow(),
+            net_outflow: flows.net_outflow(),
+            flows,
+        }
+    }
+}
+
+/// Response body for `GET /stats/survival`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SurvivalStatsResponse {
+    /// Survival time and cull rate per level.
+    pub levels: Vec<LevelSurvival>,
+    /// Exits per level and ghost streak at exit.
+    pub exit_streaks: Vec<ExitStreakCount>,
+}
+
+/// `GET /stats/survival`
+///
+/// # Errors
+///
+/// Returns `500` if a store query fails.
+pub async fn get_survival_stats<S: StatsStore>(
+    State(state): State<ApiState<S>>,
+) -> Result<Json<SurvivalStatsResponse>, ApiError> {
+    let levels = state.store.get_survival_stats().await?;
+    let exit_streaks = state.store.get_exit_streak_distribution().await?;
+
+    Ok(Json(SurvivalStatsResponse {
+        levels,
+        exit_streaks,
+    }))
+}
+
+/// Query parameters for `GET /stats/levels/:level/history`.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct LevelHistoryQuery {
+    /// Start of the range, inclusive (RFC 3339, defaults to a day before `to`).
+    pub from: Option<DateTime<Utc>>,
+    /// End of the range, exclusive (RFC 3339, defaults to now).
+    pub to: Option<DateTime<Utc>>,
+    /// Bucket size: `5m`, `1h` (the default) or `1d`.
+    pub bucket: Option<String>,
+}
+
+/// Response body for `GET /stats/levels/:level/history`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LevelHistoryResponse {
+    /// Level of the series.
+    pub level: Level,
+    /// Bucket size of the series.
+    pub bucket: HistoryBucket,
+    /// One point per bucket, oldest first, including buckets without scans.
+    pub points: Vec<LevelHistoryPoint>,
+}
+
+/// `GET /stats/levels/:level/history?from=&to=&bucket=`
+///
+/// `level` is the level's number or name (`5` or `BlackIce`).
+///
+/// # Errors
+///
+/// Returns `400` for an unknown level or a level without scans, an unknown
+/// `bucket`, a `from` not before `to`, or a range of more than
+/// [`MAX_HISTORY_POINTS`] buckets.
+pub async fn get_level_history<S: StatsStore>(
+    State(state): State<ApiState<S>>,
+    Path(level): Path<String>,
+    Query(query): Query<LevelHistoryQuery>,
+) -> Result<Json<LevelHistoryResponse>, ApiError> {
+    let level: Level = level.parse().map_err(ApiError::BadRequest)?;
+    if !level.has_scans() {
+        return Err(ApiError::BadRequest(format!("{level:?} has no scans")));
+    }
+    let bucket = query
+        .bucket
+        .as_deref()
+        .map(str::parse)
+        .transpose()
+        .map_err(ApiError::BadRequest)?
+        .unwrap_or(HistoryBucket::Hour);
+    let to = query.to.unwrap_or_else(Utc::now);
+    let from = query.from.unwrap_or(to - DEFAULT_HISTORY_RANGE);
+    if from >= to {
+        return Err(ApiError::BadRequest("from must be before to".into()));
+    }
+    let span = bucket.start_of(to - TimeDelta::nanoseconds(1)) - bucket.start_of(from);
+    if span.num_seconds() / bucket.duration().num_seconds() >= MAX_HISTORY_POINTS {
+        return Err(ApiError::BadRequest(format!(
+            "Range spans more than {MAX_HISTORY_POINTS} {bucket} buckets"
+        )));
+    }
+
+    let points = state
+        .store
+        .get_level_history(level, from, to, bucket)
+        .await?;
+    Ok(Json(LevelHistoryResponse {
+        level,
+        bucket,
+        points,
+    }))
+}
+
+/// `GET /stats/token?window_secs=&address=`
+///
+/// # Errors
+///
+/// Returns `400` for a zero `window_secs` or a malformed `address`.
+pub async fn get_token_stats<S: TokenFlowStore>(
+    State(state): State<ApiState<S>>,
+    Query(query): Query<TokenStatsQuery>,
+) -> Result<Json<TokenStatsResponse>, ApiError> {
+    let window = query
+        .window_secs
+        .map_or(state.token_flow_window, Duration::from_secs);
+    if window.is_zero() {
+        return Err(ApiError::BadRequest(
+            "window_secs must be at least 1".into(),
+        ));
+    }
+    let address = query
+        .address
+        .as_deref()
+        .map(EthAddress::from_hex)
+        .transpose()
+        .map_err(|e| ApiError::BadRequest(e.to_string()))?;
+
+    let burn_rate = state.store.get_burn_rate(window).await?;
+    let address_flows = match address {
+        Some(address) => Some(
+            state
+                .store
+                .get_address_flows(&address, window)
+                .await?
+                .into(),
+        ),
+        None => None,
+    };
+
+    Ok(Json(TokenStatsResponse {
+        burn_rate,
+        address_flows,
+    }))
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::sync::Arc;
+    use std::sync::Mutex;
+
+    use alloy::primitives::U256;
+    use async_trait::async_trait;
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use chrono::TimeZone;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::api::router;
+    use crate::config::{LeaderboardSettings, TokenFlowSettings};
+    use crate::error::{InfraError, Result};
+    use crate::indexer::LeaderboardRefresher;
+    use crate::ports::{
+        DeathStore, HolderStore, LeaderboardStore, MarketStore, PositionStore, ScanStore,
+    };
+    use crate::store::MemoryCache;
+    use crate::types::entities::{
+        Bet, CascadeEarnings, CascadeShare, Death, GlobalStats, GlobalStatsDelta, HistoryCursor,
+        HolderBalance, HolderSort, LeaderboardEntry, LevelScanStats, LevelStats, LevelStatsDelta,
+        OutboxEvent, Page, Position, PositionFilter, PositionHistoryEntry, Round, RoundSettlement,
+        Scan, ScanFinalizationData, TokenFlowDelta, TokenTransfer,
+    };
+    use crate::types::enums::LeaderboardType;
+    use crate::types::primitives::{BlockNumber, GhostStreak};
+
+    /// Store with fixed totals that records the requested windows.
+    ///
+    /// Darknet has two scans on 2026-01-01 and one on 2026-01-03.
+    #[derive(Debug, Default)]
+    #[allow(clippy::type_complexity)]
+    struct FixedStore {
+        windows: Mutex<Vec<Duration>>,
+        history_requests: Mutex<Vec<(Level, DateTime<Utc>, DateTime<Utc>, HistoryBucket)>>,
+    }
+
+    fn day(day: u32, hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 1, day, hour, 0, 0).unwrap()
+    }
+
+    fn darknet_scan(at: DateTime<Utc>, deaths: u32, survivors: u32) -> LevelScanStats {
+        LevelScanStats {
+            scan_id: at.timestamp().to_string(),
+            level: Level::Darknet,
+            scanned_at: at,
+            block_number: None,
+            deaths,
+            survivors,
+            total_staked: TokenAmount::parse(&(survivors * 100).to_string()).unwrap(),
+        }
+    }
+
+    #[async_trait]
+    impl LeaderboardStore for FixedStore {
+        async fn get_leaderboard(
+            &self,
+            _: LeaderboardType,
+            _: u32,
+        ) -> Result<Vec<LeaderboardEntry>> {
+            Ok(vec![])
+        }
+    }
+
+    #[async_trait]
+    impl TokenFlowStore for FixedStore {
+        async fn record_transfer(&self, _: &TokenTransfer) -> Result<()> {
+            Ok(())
+        }
+
+        async fn record_token_flows(&self, _: DateTime<Utc>, _: &TokenFlowDelta) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_burn_rate(&self, window: Duration) -> Result<BurnRate> {
+            self.windows.lock().unwrap().push(window);
+            Ok(BurnRate::new(
+                window,
+                TokenAmount::parse("96").unwrap(),
+                TokenAmount::parse("90").unwrap(),
+                TokenAmount::parse("10").unwrap(),
+            ))
+        }
+
+        async fn get_address_flows(
+            &self,
+            address: &EthAddress,
+            window: Duration,
+        ) -> Result<AddressFlows> {
+            Ok(AddressFlows {
+                address: *address,
+                window_secs: window.as_secs(),
+                inflow: TokenAmount::parse("25").unwrap(),
+                outflow: TokenAmount::parse("100").unwrap(),
+                transfers_in: 1,
+                transfers_out: 2,
+            })
+        }
+    }
+
+    #[async_trait]
+    impl ScanStore for FixedStore {
+        async fn save_scan(&self, _: &Scan) -> Result<()> {
+            Ok(())
+        }
+
+        async fn finalize_scan(&self, _: &str, _: ScanFinalizationData) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_recent_scans(&self, _: Level, _: u32) -> Result<Vec<Scan>> {
+            Ok(vec![])
+        }
+
+        async fn get_scan_by_id(&self, _: &str) -> Result<Option<Scan>> {
+            Ok(None)
+        }
+
+        async fn get_pending_scans(&self) -> Result<Vec<Scan>> {
+            Ok(vec![])
+        }
+
+        async fn link_deaths_to_scan(&self, _: &str, _: &[Uuid]) -> Result<u64> {
+            Ok(0)
+        }
+    }
+
+    #[async_trait]
+    impl DeathStore for FixedStore {
+        async fn record_deaths(&self, _: &[Death]) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_deaths_for_scan(&self, _: &str) -> Result<Vec<Death>> {
+            Ok(vec![])
+        }
+
+        async fn get_user_deaths(&self, _: &EthAddress, _: u32) -> Result<Vec<Death>> {
+            Ok(vec![])
+        }
+
+        async fn count_deaths_by_level(&self, _: Level) -> Result<u64> {
+            Ok(0)
+        }
+
+        async fn get_recent_deaths(&self, _: u32) -> Result<Vec<Death>> {
+            Ok(vec![])
+        }
+
+        async fn record_cascade_shares(&self, _: &[CascadeShare]) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_cascade_earnings(&self, _: &EthAddress, _: u32) -> Result<CascadeEarnings> {
+            Ok(CascadeEarnings {
+                total: TokenAmount::zero(),
+                scans: vec![],
+            })
+        }
+    }
+
+    #[async_trait]
+    impl PositionStore for FixedStore {
+        async fn get_active_position(&self, _: &EthAddress) -> Result<Option<Position>> {
+            Ok(None)
+        }
+
+        async fn save_position(&self, _: &Position) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_at_risk_positions(&self, _: Level, _: u32) -> Result<Vec<Position>> {
+            Ok(vec![])
+        }
+
+        async fn append_history(&self, _: &PositionHistoryEntry) -> Result<()> {
+            Ok(())
+        }
+
+        async fn save_position_with_history(
+            &self,
+            _: &Position,
+            _: &PositionHistoryEntry,
+            _: &[OutboxEvent],
+        ) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_history(
+            &self,
+            _: &EthAddress,
+            _: u32,
+            _: Option<HistoryCursor>,
+        ) -> Result<Vec<PositionHistoryEntry>> {
+            Ok(vec![])
+        }
+
+        async fn get_position_by_id(&self, _: &Uuid) -> Result<Option<Position>> {
+            Ok(None)
+        }
+
+        async fn get_positions_by_level(&self, _: Level) -> Result<Vec<Position>> {
+            Ok(vec![])
+        }
+
+        async fn query(&self, _: &PositionFilter, _: u32) -> Result<Page<Position>> {
+            Ok(Page {
+                items: vec![],
+                next_cursor: None,
+            })
+        }
+
+        async fn count_positions_by_level(&self, _: Level) -> Result<u32> {
+            Ok(0)
+        }
+
+        async fn count_scans_survived(
+            &self,
+            _: Level,
+            _: BlockNumber,
+            _: BlockNumber,
+        ) -> Result<u32> {
+            Ok(0)
+        }
+    }
+
+    #[async_trait]
+    impl MarketStore for FixedStore {
+        async fn save_round(&self, _: &Round) -> Result<()> {
+            Ok(())
+        }
+
+        async fn record_bet(&self, _: &Bet) -> Result<()> {
+            Ok(())
+        }
+
+        async fn resolve_round(&self, _: &str, _: &RoundSettlement) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_active_rounds(&self, _: u32) -> Result<Vec<Round>> {
+            Ok(vec![])
+        }
+
+        async fn get_round_by_id(&self, _: &str) -> Result<Option<Round>> {
+            Ok(None)
+        }
+
+        async fn get_bets_for_round(&self, _: &str) -> Result<Vec<Bet>> {
+            Ok(vec![])
+        }
+
+        async fn get_user_bets(&self, _: &EthAddress, _: u32) -> Result<Vec<Bet>> {
+            Ok(vec![])
+        }
+
+        async fn mark_bet_claimed(&self, _: &str, _: &EthAddress, _: &TokenAmount) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl StatsStore for FixedStore {
+        async fn get_global_stats(&self) -> Result<GlobalStats> {
+            Err(InfraError::NotFound.into())
+        }
+
+        async fn get_level_stats(&self, _: Level) -> Result<LevelStats> {
+            Err(InfraError::NotFound.into())
+        }
+
+        async fn update_level_stats(&self, _: Level, _: LevelStatsDelta) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_all_level_stats(&self) -> Result<Vec<LevelStats>> {
+            Ok(vec![])
+        }
+
+        async fn refresh_global_stats(&self) -> Result<GlobalStats> {
+            Err(InfraError::NotFound.into())
+        }
+
+        async fn update_global_stats(&self, _: GlobalStatsDelta) -> Result<()> {
+            Ok(())
+        }
+
+        async fn recompute_level_stats(&self) -> Result<Vec<LevelStats>> {
+            Ok(vec![])
+        }
+
+        async fn get_recent_death_counts(
+            &self,
+            _: DateTime<Utc>,
+        ) -> Result<Vec<(Level, DateTime<Utc>, u32)>> {
+            Ok(vec![])
+        }
+
+        async fn get_survival_stats(&self) -> Result<Vec<LevelSurvival>> {
+            Ok(vec![LevelSurvival {
+                level: Level::Darknet,
+                exits: 4,
+                culled: 1,
+                cull_rate: 0.25,
+                average_survival_seconds: Some(5400.0),
+                average_scans_survived: Some(2.5),
+            }])
+        }
+
+        async fn get_exit_streak_distribution(&self) -> Result<Vec<ExitStreakCount>> {
+            Ok(vec![
+                ExitStreakCount {
+                    level: Level::Darknet,
+                    ghost_streak: GhostStreak::new(0).unwrap(),
+                    exits: 1,
+                },
+                ExitStreakCount {
+                    level: Level::Darknet,
+                    ghost_streak: GhostStreak::new(3).unwrap(),
+                    exits: 3,
+                },
+            ])
+        }
+
+        async fn record_level_scans(&self, _: &[LevelScanStats]) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_level_history(
+            &self,
+            level: Level,
+            from: DateTime<Utc>,
+            to: DateTime<Utc>,
+            bucket: HistoryBucket,
+        ) -> Result<Vec<LevelHistoryPoint>> {
+            self.history_requests
+                .lock()
+                .unwrap()
+                .push((level, from, to, bucket));
+            let scans = [
+                darknet_scan(day(1, 6), 4, 6),
+                darknet_scan(day(1, 18), 2, 8),
+                darknet_scan(day(3, 6), 0, 10),
+            ];
+            let scans = if level == Level::Darknet { &scans[..] } else { &[] };
+            Ok(LevelHistoryPoint::series(scans, from, to, bucket))
+        }
+    }
+
+    #[async_trait]
+    impl HolderStore for FixedStore {
+        async fn get_holder(&self, _: &EthAddress) -> Result<Option<HolderBalance>> {
+            Ok(None)
+        }
+
+        async fn save_holder(&self, _: &HolderBalance) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_top_holders(&self, _: HolderSort, _: u32) -> Result<Vec<HolderBalance>> {
+            Ok(vec![])
+        }
+
+        async fn count_holders(&self) -> Result<u64> {
+            Ok(0)
+        }
+
+        async fn get_holders_to_reconcile(&self, _: u32) -> Result<Vec<HolderBalance>> {
+            Ok(vec![])
+        }
+
+        async fn reconcile_holder(
+            &self,
+            _: &HolderBalance,
+            _: U256,
+            _: DateTime<Utc>,
+        ) -> Result<bool> {
+            Ok(false)
+        }
+    }
+
+    fn app() -> (axum::Router, Arc<FixedStore>) {
+        let store = Arc::new(FixedStore::default());
+        let leaderboard = LeaderboardSettings::default();
+        let refresher = Arc::new(LeaderboardRefresher::new(
+            Arc::clone(&store),
+            Arc::new(MemoryCache::new()),
+            &leaderboard,
+        ));
+        let token_flows = TokenFlowSettings {
+            default_window_secs: 7200,
+            ..TokenFlowSettings::default()
+        };
+        let state = ApiState::new(Arc::clone(&store), refresher, &leaderboard, &token_flows);
+        (router(state), store)
+    }
+
+    async fn get(app: &axum::Router, uri: &str) -> (StatusCode, serde_json::Value) {
+        let response = app
+            .clone()
+            .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn returns_burn_rate_for_default_window() {
+        let (app, store) = app();
+
+        let (status, body) = get(&app, "/api/v1/stats/token").await;
+
+        assert_eq!(status, StatusCode::OK);
+        let response: TokenStatsResponse = serde_json::from_value(body).unwrap();
+        assert_eq!(response.burn_rate.window_secs, 7200);
+        assert_eq!(response.burn_rate.burned_per_hour.to_string(), "48");
+        assert!(response.address_flows.is_none());
+        assert_eq!(*store.windows.lock().unwrap(), [Duration::from_secs(7200)]);
+    }
+
+    #[tokio::test]
+    async fn includes_address_flows_with_net_direction() {
+        let (app, _) = app();
+
+        let (status, body) = get(
+            &app,
+            "/api/v1/stats/token?window_secs=3600&address=0x1234567890123456789012345678901234567890",
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::OK);
+        let flows = &body["address_flows"];
+        assert_eq!(flows["window_secs"], 3600);
+        assert_eq!(flows["net_inflow"], "0");
+        assert_eq!(flows["net_outflow"], "75");
+        assert_eq!(flows["transfers_out"], 2);
+    }
+
+    #[tokio::test]
+    async fn rejects_malformed_address() {
+        let (app, _) = app();
+
+        let (status, body) = get(&app, "/api/v1/stats/token?address=0xnothex").await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"]["code"], "BAD_REQUEST");
+    }
+
+    #[tokio::test]
+    async fn rejects_zero_window() {
+        let (app, _) = app();
+
+        let (status, _) = get(&app, "/api/v1/stats/token?window_secs=0").await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn returns_survival_stats() {
+        let (app, _) = app();
+
+        let (status, body) = get(&app, "/api/v1/stats/survival").await;
+
+        assert_eq!(status, StatusCode::OK);
+        let response: SurvivalStatsResponse = serde_json::from_value(body).unwrap();
+        assert_eq!(response.levels.len(), 1);
+        let darknet = &response.levels[0];
+        assert_eq!(darknet.level, Level::Darknet);
+        assert_eq!(darknet.culled, 1);
+        assert_eq!(darknet.average_survival_seconds, Some(5400.0));
+        let exits: u32 = response.exit_streaks.iter().map(|s| s.exits).sum();
+        assert_eq!(exits, darknet.exits);
+    }
+
+    #[tokio::test]
+    #[allow(clippy::panic)]
+    async fn returns_daily_level_history_with_empty_days() {
+        let (app, _) = app();
+
+        let uri = "/api/v1/stats/levels/Darknet/history\
+                   ?from=2026-01-01T00:00:00Z&to=2026-01-04T00:00:00Z&bucket=1d";
+        let (status, body) = get(&app, uri).await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["bucket"], "1d");
+        let response: LevelHistoryResponse = serde_json::from_value(body).unwrap();
+        assert_eq!(response.level, Level::Darknet);
+        let [first, empty, last] = response.points.as_slice() else {
+            panic!("expected three days, got {:?}", response.points);
+        };
+        assert_eq!(first.timestamp, day(1, 0));
+        // 6 of the 20 positions scanned on the first day died
+        assert_eq!((first.scans, first.deaths), (2, 6));
+        assert!((first.death_rate.unwrap() - 0.3).abs() < f64::EPSILON);
+        assert_eq!(first.survivor_count, Some(8));
+        assert_eq!((empty.scans, empty.death_rate), (0, None));
+        assert_eq!((empty.survivor_count, &empty.tvl), (Some(8), &first.tvl));
+        assert_eq!(last.death_rate, Some(0.0));
+        assert_eq!(last.tvl, Some(TokenAmount::parse("1000").unwrap()));
+    }
+
+    #[tokio::test]
+    #[allow(clippy::panic)]
+    async fn level_history_defaults_to_a_day_of_hours() {
+        let (app, store) = app();
+
+        let (status, body) = get(&app, "/api/v1/stats/levels/4/history").await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["bucket"], "1h");
+        let requests = store.history_requests.lock().unwrap().clone();
+        let [(level, from, to, bucket)] = requests.as_slice() else {
+            panic!("expected one history request, got {requests:?}");
+        };
+        assert_eq!((*level, *bucket), (Level::Darknet, HistoryBucket::Hour));
+        assert_eq!(*to - *from, DEFAULT_HISTORY_RANGE);
+        assert_eq!(body["points"].as_array().unwrap().len(), 25);
+    }
+
+    #[tokio::test]
+    async fn rejects_bad_level_history_requests() {
+        let (app, store) = app();
+        let from = "from=2026-01-01T00:00:00Z";
+
+        for query in [
+            "levels/Vault/history?",
+            "levels/9/history?",
+            "levels/Darknet/history?bucket=15m&",
+            "levels/Darknet/history?to=2026-01-01T00:00:00Z&",
+            "levels/Darknet/history?to=2026-01-05T00:00:00Z&bucket=5m&",
+        ] {
+            let uri = format!("/api/v1/stats/{query}{from}");
+            let (status, _) = get(&app, &uri).await;
+            assert_eq!(status, StatusCode::BAD_REQUEST, "{uri}");
+        }
+        assert!(store.history_requests.lock().unwrap().is_empty());
+
+        // Exactly the most points a request may span
+        let uri = "/api/v1/stats/levels/Darknet/history\
+                   ?from=2026-01-01T00:00:00Z&to=2026-01-04T11:20:00Z&bucket=5m";
+        let (status, body) = get(&app, uri).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["points"].as_array().unwrap().len(), 1000);
+    }
+}
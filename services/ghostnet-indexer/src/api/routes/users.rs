@@ -0,0 +1,37 @@
+//! User profile routes.
+
+use axum::Json;
+use axum::extract::{Path, State};
+
+use crate::api::ApiState;
+use crate::error::ApiError;
+use crate::indexer::UserProfile;
+use crate::ports::{DeathStore, MarketStore, PositionStore, TokenFlowStore};
+use crate::types::primitives::EthAddress;
+
+/// `GET /users/:address`
+///
+/// Sections the address has no activity in are `null`. Sections that took
+/// too long are `null` as well and listed in `timed_out`, with `partial`
+/// set.
+///
+/// # Errors
+///
+/// Returns `400` for a malformed `address`, `404` if user profiles are not
+/// configured, and `500` if a store query fails.
+pub async fn get_user_profile<S>(
+    State(state): State<ApiState<S>>,
+    Path(address): Path<String>,
+) -> Result<Json<UserProfile>, ApiError>
+where
+    S: PositionStore + DeathStore + MarketStore + TokenFlowStore,
+{
+    let profiles = state
+        .user_profiles
+        .as_ref()
+        .ok_or_else(|| ApiError::NotFound("user profiles".into()))?;
+    let address =
+        EthAddress::from_hex(&address).map_err(|e| ApiError::BadRequest(e.to_string()))?;
+
+    Ok(Json(profiles.profile(&address).await?))
+}
@@ -0,0 +1,231 @@
+//! Router assembly and HTTP server lifecycle.
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use tokio_util::sync::CancellationToken;
+use tower_http::trace::TraceLayer;
+use tracing::info;
+
+use super::ApiState;
+use super::rate_limit::{HEALTH_PATH, RateLimiter, limit_requests};
+use super::routes::{chain, events, holders, leaderboards, positions, rounds, scans, stats, users};
+use crate::config::ApiSettings;
+use crate::error::{InfraError, Result};
+use crate::ports::{
+    DeathStore, HolderStore, LeaderboardStore, MarketStore, PositionStore, ScanStore, StatsStore,
+    TokenFlowStore,
+};
+
+/// Build the API router.
+pub fn router<S>(state: ApiState<S>) -> Router
+where
+    S: LeaderboardStore
+        + TokenFlowStore
+        + ScanStore
+        + DeathStore
+        + PositionStore
+        + StatsStore
+        + MarketStore
+        + HolderStore
+        + 'static,
+{
+    let limiter = state.rate_limiter.clone();
+    let app = Router::new()
+        .nest("/api/v1", v1_routes())
+        .route(HEALTH_PATH, get(health::<S>))
+        .with_state(state);
+    finish(app, limiter)
+}
+
+/// Build the API router of several chains, given as `(chain_id, state)`.
+///
+/// The routes of each chain are nested under `/api/v1/chains/:chain_id`,
+/// and those of the `primary` chain under `/api/v1` too. `GET /health`
+/// reports the database of every chain, with 503 if any is unusable.
+/// Requests are rate limited by the limiter of the primary chain.
+pub fn chains_router<S>(primary: u64, chains: Vec<(u64, ApiState<S>)>) -> Router
+where
+    S: LeaderboardStore
+        + TokenFlowStore
+        + ScanStore
+        + DeathStore
+        + PositionStore
+        + StatsStore
+        + MarketStore
+        + HolderStore
+        + 'static,
+{
+    let mut app = Router::new();
+    let mut limiter = None;
+    for (chain_id, state) in &chains {
+        if *chain_id == primary {
+            limiter.clone_from(&state.rate_limiter);
+            app = app.nest("/api/v1", v1_routes().with_state(state.clone()));
+        }
+        let path = format!("/api/v1/chains/{chain_id}");
+        app = app.nest(&path, v1_routes().with_state(state.clone()));
+    }
+    let health = Router::new()
+        .route(HEALTH_PATH, get(chains_health::<S>))
+        .with_state(Arc::<[_]>::from(chains));
+    finish(app.merge(health), limiter)
+}
+
+/// Routes served under `/api/v1`.
+fn v1_routes<S>() -> Router<ApiState<S>>
+where
+    S: LeaderboardStore
+        + TokenFlowStore
+        + ScanStore
+        + DeathStore
+        + PositionStore
+        + StatsStore
+        + MarketStore
+        + HolderStore
+        + 'static,
+{
+    Router::new()
+        .route(
+            "/chain/read/:contract/:method",
+            get(chain::read_contract::<S>),
+        )
+        .route(
+            "/leaderboard/:type",
+            get(leaderboards::get_leaderboard::<S>),
+        )
+        .route("/positions", get(positions::list_positions::<S>))
+        .route("/positions/:address", get(positions::get_position::<S>))
+        .route(
+            "/positions/:address/cascades",
+            get(positions::get_cascade_earnings::<S>),
+        )
+        .route(
+            "/positions/:address/history",
+            get(positions::get_position_history::<S>),
+        )
+        .route(
+            "/rounds/:id/odds-history",
+            get(rounds::get_odds_history::<S>),
+        )
+        .route("/scans/next", get(scans::get_next_scans::<S>))
+        .route("/scans/:id", get(scans::get_scan::<S>))
+        .route(
+            "/stats/levels/:level/history",
+            get(stats::get_level_history::<S>),
+        )
+        .route("/stats/survival", get(stats::get_survival_stats::<S>))
+        .route("/stats/token", get(stats::get_token_stats::<S>))
+        .route("/token/holders", get(holders::list_holders::<S>))
+        .route("/token/holders/:address", get(holders::get_holder::<S>))
+        .route("/users/:address", get(users::get_user_profile::<S>))
+        .route("/ws", get(events::stream_events::<S>))
+}
+
+/// Rate limit `app` by `limiter`, if any, and trace its requests.
+fn finish(app: Router, limiter: Option<Arc<RateLimiter>>) -> Router {
+    let app = match limiter {
+        Some(limiter) => app.layer(axum::middleware::from_fn_with_state(
+            limiter,
+            limit_requests,
+        )),
+        None => app,
+    };
+    app.layer(TraceLayer::new_for_http())
+}
+
+/// `GET /health`: the server is up and, given a health store, whether the
+/// database is usable, and given publisher health, the publisher's delivery
+/// counters, with 503 if either is degraded.
+async fn health<S: Send + Sync>(State(state): State<ApiState<S>>) -> Response {
+    let (healthy, mut body) = components_health(&state).await;
+    let (code, status) = health_status(healthy);
+    body.insert("status".into(), status.into());
+    (code, Json(body)).into_response()
+}
+
+/// Health of the database and publisher of `state`, whichever it has, and
+/// whether they are all healthy.
+async fn components_health<S: Sync>(
+    state: &ApiState<S>,
+) -> (bool, serde_json::Map<String, serde_json::Value>) {
+    let mut healthy = true;
+    let mut body = serde_json::Map::new();
+    if let Some(store) = &state.health_store {
+        let database = store.health().await;
+        healthy &= database.is_healthy();
+        body.insert("database".into(), serde_json::json!(database));
+    }
+    if let Some(publisher) = &state.publisher_health {
+        let (counters, publishing) = publisher.check();
+        healthy &= publishing;
+        let mut publisher = serde_json::json!(counters);
+        publisher["healthy"] = publishing.into();
+        body.insert("publisher".into(), publisher);
+    }
+    (healthy, body)
+}
+
+/// Status code and `status` of a healthy or degraded server.
+const fn health_status(healthy: bool) -> (StatusCode, &'static str) {
+    if healthy {
+        (StatusCode::OK, "ok")
+    } else {
+        (StatusCode::SERVICE_UNAVAILABLE, "degraded")
+    }
+}
+
+/// State of each chain served, by chain ID.
+type ChainStates<S> = Arc<[(u64, ApiState<S>)]>;
+
+/// `GET /health` of several chains: the database and publisher of each
+/// chain that reports them, with 503 if any is degraded.
+async fn chains_health<S: Send + Sync>(
+    State(chains): State<ChainStates<S>>,
+) -> Response {
+    let mut healthy = true;
+    let mut components = serde_json::Map::new();
+    for (chain_id, state) in chains.iter() {
+        let (chain_healthy, chain) = components_health(state).await;
+        healthy &= chain_healthy;
+        if !chain.is_empty() {
+            components.insert(chain_id.to_string(), chain.into());
+        }
+    }
+    let (code, status) = health_status(healthy);
+    let body = serde_json::json!({ "status": status, "chains": components });
+    (code, Json(body)).into_response()
+}
+
+/// Serve `router` on the configured address until `shutdown` is cancelled.
+///
+/// Requests carry the peer address as [`ConnectInfo`](axum::extract::ConnectInfo),
+/// which the rate limiter falls back to for the client IP.
+///
+/// # Errors
+///
+/// Returns an error if the address cannot be bound or the server fails.
+pub async fn serve(
+    settings: &ApiSettings,
+    router: Router,
+    shutdown: CancellationToken,
+) -> Result<()> {
+    let addr = settings.socket_addr();
+    let listener = tokio::net::TcpListener::bind(&addr)
+        .await
+        .map_err(|e| InfraError::Internal(format!("Failed to bind API server to {addr}: {e}")))?;
+    info!(%addr, "API server listening");
+
+    axum::serve(
+        listener,
+        router.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .with_graceful_shutdown(async move { shutdown.cancelled().await })
+    .await
+    .map_err(|e| InfraError::Internal(format!("API server error: {e}")).into())
+}
@@ -14,9 +14,16 @@
 //! println!("RPC URL: {}", settings.rpc.url);
 //! ```
 
+mod reload;
 mod settings;
 
+pub use reload::{ConfigReloader, ReloadOutcome, SettingsLoader};
+
 pub use settings::{
-    ApiSettings, CacheSettings, ContractAddresses, DatabaseSettings, IggySettings, LoggingSettings,
-    MetricsSettings, RateLimitSettings, RpcSettings, Settings, WebSocketSettings,
+    ApiSettings, BoostSettings, CacheSettings, ChainSettings, ContractAddresses, DatabaseSettings,
+    HolderSettings, IggySettings, IndexedChain, LeaderboardSettings, LoggingSettings,
+    MetricsSettings, OutboxSettings, QuarantineSettings, RateLimitSettings, RawLogSettings,
+    RoundWatcherSettings, RpcSettings, ScanPredictionSettings, SchemaSettings, Settings,
+    ShutdownSettings, StatsSettings, TokenFlowSettings, TxContextSettings, UserProfileSettings,
+    WebSocketSettings,
 };
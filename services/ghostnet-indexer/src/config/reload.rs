@@ -0,0 +1,508 @@
+//! Hot reload of runtime-changeable settings.
+//!
+//! The indexer re-reads its configuration on SIGHUP. [`ConfigReloader`]
+//! re-parses and validates the settings, then applies the fields that can
+//! change while running:
+//!
+//! | Field | Applied by |
+//! |-------|------------|
+//! | `cache.*` | Rebuilding the caches with [`MemoryCache::reconfigure`], entries carried over |
+//! | `contracts` addresses, `disabled`, `additional` | Replacing the [`SharedRegistry`]; processors filter with it from their next block range or subscription |
+//! | `api.rate_limit.*` | Publishing the settings to [`ConfigReloader::subscribe`], followed by the API's [`RateLimiter`](crate::api::RateLimiter) |
+//!
+//! Any other change (e.g. `database` or `rpc.chain_id`) is logged as
+//! requiring a restart and not applied; the settings in effect keep the old
+//! value, so the warning repeats on every reload until the restart. A reload
+//! that fails to parse or validate leaves everything as it was.
+//!
+//! Only the single chain of `rpc` and `contracts` is reloaded. An indexer
+//! running `[[chains]]` does not reload on SIGHUP, and takes a restart for
+//! any change.
+//!
+//! Each reload is counted by `indexer_config_reloads_total` and logs the
+//! fields it changed.
+
+use std::sync::Arc;
+
+use alloy::providers::Provider;
+use tokio::sync::{mpsc, watch};
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use super::{ApiSettings, ContractAddresses, Settings};
+use crate::error::{AppError, Result};
+use crate::indexer::{Contract, ContractRegistry, SharedRegistry};
+use crate::obs;
+use crate::store::MemoryCache;
+
+/// Loads the settings to reload, e.g. from the file the indexer started with.
+pub type SettingsLoader = Box<dyn Fn() -> Result<Settings> + Send + Sync>;
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// RELOAD OUTCOME
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Fields changed by a reload.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReloadOutcome {
+    /// Changed fields that were applied.
+    pub applied: Vec<String>,
+    /// Changed fields that only take effect after a restart.
+    pub restart_required: Vec<String>,
+}
+
+impl ReloadOutcome {
+    /// Compare the settings in effect with reloaded ones.
+    fn between(old: &Settings, new: &Settings) -> Self {
+        Self {
+            applied: Self::reloadable_changes(old, new),
+            restart_required: Self::restart_changes(old, new),
+        }
+    }
+
+    /// Fields changed between `old` and `new` that apply while running.
+    fn reloadable_changes(old: &Settings, new: &Settings) -> Vec<String> {
+        let (cache, rate_limit) = (
+            (&old.cache, &new.cache),
+            (&old.api.rate_limit, &new.api.rate_limit),
+        );
+        let mut reloadable = changed([
+            (
+                "cache.positions_ttl_ms",
+                cache.0.positions_ttl_ms != cache.1.positions_ttl_ms,
+            ),
+            (
+                "cache.positions_max_capacity",
+                cache.0.positions_max_capacity != cache.1.positions_max_capacity,
+            ),
+            (
+                "cache.leaderboard_ttl_ms",
+                cache.0.leaderboard_ttl_ms != cache.1.leaderboard_ttl_ms,
+            ),
+            (
+                "cache.leaderboard_max_capacity",
+                cache.0.leaderboard_max_capacity != cache.1.leaderboard_max_capacity,
+            ),
+            (
+                "cache.stats_ttl_ms",
+                cache.0.stats_ttl_ms != cache.1.stats_ttl_ms,
+            ),
+            (
+                "api.rate_limit.requests_per_minute",
+                rate_limit.0.requests_per_minute != rate_limit.1.requests_per_minute,
+            ),
+            (
+                "api.rate_limit.burst_size",
+                rate_limit.0.burst_size != rate_limit.1.burst_size,
+            ),
+            (
+                "api.rate_limit.websocket_connects_per_minute",
+                rate_limit.0.websocket_connects_per_minute
+                    != rate_limit.1.websocket_connects_per_minute,
+            ),
+            (
+                "api.rate_limit.subscribe_messages_per_minute",
+                rate_limit.0.subscribe_messages_per_minute
+                    != rate_limit.1.subscribe_messages_per_minute,
+            ),
+            (
+                "api.rate_limit.trusted_proxy_header",
+                rate_limit.0.trusted_proxy_header != rate_limit.1.trusted_proxy_header,
+            ),
+            (
+                "contracts.disabled",
+                old.contracts.disabled != new.contracts.disabled,
+            ),
+            (
+                "contracts.additional",
+                old.contracts.additional != new.contracts.additional,
+            ),
+        ]);
+        reloadable.extend(
+            Contract::ALL
+                .into_iter()
+                .filter(|contract| {
+                    contract.configured_address(&old.contracts)
+                        != contract.configured_address(&new.contracts)
+                })
+                .map(|contract| format!("contracts.{contract}")),
+        );
+        reloadable
+    }
+
+    /// Fields changed between `old` and `new` that take a restart.
+    fn restart_changes(old: &Settings, new: &Settings) -> Vec<String> {
+        // The API section minus its reloadable rate limits
+        let api = ApiSettings {
+            rate_limit: new.api.rate_limit.clone(),
+            ..old.api.clone()
+        };
+        changed([
+            ("rpc", old.rpc != new.rpc),
+            ("database", old.database != new.database),
+            ("iggy", old.iggy != new.iggy),
+            ("api", api != new.api),
+            ("stats", old.stats != new.stats),
+            ("leaderboard", old.leaderboard != new.leaderboard),
+            ("token_flows", old.token_flows != new.token_flows),
+            ("tx_context", old.tx_context != new.tx_context),
+            ("scan_prediction", old.scan_prediction != new.scan_prediction),
+            ("shutdown", old.shutdown != new.shutdown),
+            ("logging", old.logging != new.logging),
+            ("metrics", old.metrics != new.metrics),
+            ("chains", old.chains != new.chains),
+            ("primary_chain", old.primary_chain != new.primary_chain),
+            (
+                "contracts.code_hashes",
+                old.contracts.code_hashes != new.contracts.code_hashes,
+            ),
+        ])
+    }
+}
+
+/// Names of the `fields` marked as changed.
+fn changed<const N: usize>(fields: [(&str, bool); N]) -> Vec<String> {
+    fields
+        .into_iter()
+        .filter(|(_, changed)| *changed)
+        .map(|(field, _)| field.to_string())
+        .collect()
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// CONFIG RELOADER
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Re-reads the settings on request and applies what can change at runtime.
+pub struct ConfigReloader<P> {
+    /// Source of the reloaded settings.
+    loader: SettingsLoader,
+    /// Settings in effect.
+    current: watch::Sender<Settings>,
+    /// Cache whose TTLs and capacities are reloaded.
+    cache: Arc<MemoryCache>,
+    /// Contracts the processors watch.
+    contracts: SharedRegistry,
+    /// Provider used to verify reloaded contracts.
+    provider: P,
+}
+
+impl<P> std::fmt::Debug for ConfigReloader<P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConfigReloader")
+            .field("contracts", &self.contracts)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<P: Provider> ConfigReloader<P> {
+    /// Create a reloader for the components built from `settings`.
+    #[must_use]
+    pub fn new(
+        settings: Settings,
+        loader: SettingsLoader,
+        cache: Arc<MemoryCache>,
+        contracts: SharedRegistry,
+        provider: P,
+    ) -> Self {
+        Self {
+            loader,
+            current: watch::Sender::new(settings),
+            cache,
+            contracts,
+            provider,
+        }
+    }
+
+    /// Watch the settings in effect, updated after each reload.
+    #[must_use]
+    pub fn subscribe(&self) -> watch::Receiver<Settings> {
+        self.current.subscribe()
+    }
+
+    /// Reload the settings and apply the runtime-changeable fields.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error, and applies nothing, if the settings cannot be
+    /// loaded or are invalid, or if a reloaded contract fails verification.
+    pub async fn reload(&self) -> Result<ReloadOutcome> {
+        let result = self.try_reload().await;
+        obs::record_config_reload(result.is_ok());
+
+        match &result {
+            Ok(outcome) => {
+                info!(applied = ?outcome.applied, "Configuration reloaded");
+                if !outcome.restart_required.is_empty() {
+                    warn!(
+                        fields = ?outcome.restart_required,
+                        "Changed settings require a restart to take effect"
+                    );
+                }
+            }
+            Err(e) => warn!(error = %e, "Configuration reload failed, keeping current settings"),
+        }
+        result
+    }
+
+    async fn try_reload(&self) -> Result<ReloadOutcome> {
+        let reloaded = (self.loader)()?;
+        reloaded
+            .validate()
+            .map_err(|errors| AppError::Config(errors.join("; ")))?;
+
+        let mut settings = self.current.borrow().clone();
+        let outcome = ReloadOutcome::between(&settings, &reloaded);
+
+        // Code hashes are only checked against the contracts at startup
+        let contracts = ContractAddresses {
+            code_hashes: settings.contracts.code_hashes.clone(),
+            ..reloaded.contracts
+        };
+        if contracts != settings.contracts {
+            let registry = ContractRegistry::from_config(&contracts)?;
+            registry.verify(&self.provider).await?;
+            self.contracts.replace(Arc::new(registry));
+            settings.contracts = contracts;
+        }
+
+        if reloaded.cache != settings.cache {
+            self.cache.reconfigure(&reloaded.cache);
+            settings.cache = reloaded.cache;
+        }
+        settings.api.rate_limit = reloaded.api.rate_limit;
+
+        self.current.send_replace(settings);
+        Ok(outcome)
+    }
+
+    /// Reload on every request until `shutdown` is cancelled or the
+    /// requests channel closes.
+    pub async fn run(self, mut requests: mpsc::Receiver<()>, shutdown: CancellationToken) {
+        loop {
+            tokio::select! {
+                () = shutdown.cancelled() => return,
+                request = requests.recv() => {
+                    if request.is_none() {
+                        return;
+                    }
+                    // Logged and counted by `reload`
+                    let _ = self.reload().await;
+                }
+            }
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::path::{Path, PathBuf};
+    use std::time::Duration;
+
+    use alloy::primitives::Address;
+    use alloy::providers::ProviderBuilder;
+    use alloy::transports::mock::Asserter;
+
+    use super::*;
+    use crate::config::CacheSettings;
+    use crate::error::InfraError;
+    use crate::ports::Cache;
+    use crate::types::primitives::EthAddress;
+
+    const PREVIOUS_DEAD_POOL: &str = "0x0000000000000000000000000000000000000033";
+
+    /// A config file in the temp directory, removed on drop.
+    struct TempConfig(PathBuf);
+
+    impl TempConfig {
+        fn new(contents: &str) -> Self {
+            let path = std::env::temp_dir()
+                .join(format!("ghostnet-indexer-{}.toml", uuid::Uuid::new_v4()));
+            let config = Self(path);
+            config.write(contents);
+            config
+        }
+
+        fn write(&self, contents: &str) {
+            std::fs::write(&self.0, contents).unwrap();
+        }
+
+        fn loader(&self) -> SettingsLoader {
+            let path = self.0.clone();
+            Box::new(move || load(&path))
+        }
+    }
+
+    impl Drop for TempConfig {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_file(&self.0);
+        }
+    }
+
+    fn load(path: &Path) -> Result<Settings> {
+        Ok(Settings::from_file_with_defaults(path).map_err(InfraError::Config)?)
+    }
+
+    fn config_with(positions_ttl_ms: u64, extra: &str) -> String {
+        format!(
+            "[cache]\n\
+             positions_ttl_ms = {positions_ttl_ms}\n\
+             {extra}\n"
+        )
+    }
+
+    struct Fixture {
+        file: TempConfig,
+        cache: Arc<MemoryCache>,
+        contracts: SharedRegistry,
+        reloader: ConfigReloader<alloy::providers::DynProvider>,
+    }
+
+    fn fixture() -> Fixture {
+        let file = TempConfig::new(&config_with(5000, ""));
+        let settings = load(&file.0).unwrap();
+        let cache = Arc::new(MemoryCache::from_settings(&settings.cache));
+        let contracts =
+            SharedRegistry::from(ContractRegistry::from_config(&settings.contracts).unwrap());
+        // Contract code lookups fail, so reloaded contracts stay unverified
+        let provider = ProviderBuilder::new()
+            .connect_mocked_client(Asserter::new())
+            .erased();
+        let reloader = ConfigReloader::new(
+            settings,
+            file.loader(),
+            Arc::clone(&cache),
+            contracts.clone(),
+            provider,
+        );
+        Fixture {
+            file,
+            cache,
+            contracts,
+            reloader,
+        }
+    }
+
+    #[tokio::test]
+    async fn reload_signal_applies_cache_ttls_and_contracts() {
+        let Fixture {
+            file,
+            cache,
+            contracts,
+            reloader,
+        } = fixture();
+        let mut settings = reloader.subscribe();
+        let address = EthAddress::from_hex("0x1234567890123456789012345678901234567890").unwrap();
+        cache.set_position(&address, None);
+
+        // A block range in flight holds on to the registry it started with
+        let in_flight = contracts.current();
+
+        let (requests, receiver) = mpsc::channel(1);
+        let shutdown = CancellationToken::new();
+        let task = tokio::spawn(reloader.run(receiver, shutdown.clone()));
+
+        file.write(&config_with(
+            1234,
+            &format!(
+                "[contracts.additional]\ndead_pool = [\"{PREVIOUS_DEAD_POOL}\"]\n\
+                 [database]\nurl = \"postgres://elsewhere/ghostnet\"\n"
+            ),
+        ));
+        requests.send(()).await.unwrap();
+        tokio::time::timeout(Duration::from_secs(5), settings.changed())
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(cache.position_ttl(), Some(Duration::from_millis(1234)));
+        assert!(cache.get_position(&address).is_none(), "entry carried over");
+        assert_eq!(cache.stats().hits, 1);
+
+        let previous: Address = PREVIOUS_DEAD_POOL.parse().unwrap();
+        assert!(contracts.current().addresses().contains(&previous));
+        assert!(!in_flight.addresses().contains(&previous));
+
+        // The database URL needs a restart, so the old one stays in effect
+        let in_effect = settings.borrow_and_update().clone();
+        assert_eq!(in_effect.cache.positions_ttl_ms, 1234);
+        assert_eq!(in_effect.database.url, "postgres://localhost/ghostnet");
+
+        shutdown.cancel();
+        task.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn reload_reports_changed_fields() {
+        let Fixture { file, reloader, .. } = fixture();
+
+        assert_eq!(reloader.reload().await.unwrap(), ReloadOutcome::default());
+
+        file.write(&config_with(
+            5000,
+            "stats_ttl_ms = 1\n\
+             [api.rate_limit]\nrequests_per_minute = 5\n\
+             [rpc]\nchain_id = 6342\n\
+             [contracts]\nghost_core = \"0x00000000000000000000000000000000000000aa\"\n",
+        ));
+        let outcome = reloader.reload().await.unwrap();
+        assert_eq!(
+            outcome.applied,
+            [
+                "cache.stats_ttl_ms",
+                "api.rate_limit.requests_per_minute",
+                "contracts.ghost_core"
+            ]
+        );
+        assert_eq!(outcome.restart_required, ["rpc"]);
+        assert_eq!(
+            reloader
+                .subscribe()
+                .borrow()
+                .api
+                .rate_limit
+                .requests_per_minute,
+            5
+        );
+
+        // Still pending a restart on the next reload
+        let outcome = reloader.reload().await.unwrap();
+        assert!(outcome.applied.is_empty());
+        assert_eq!(outcome.restart_required, ["rpc"]);
+    }
+
+    #[tokio::test]
+    async fn invalid_reload_changes_nothing() {
+        let Fixture {
+            file,
+            cache,
+            contracts,
+            reloader,
+        } = fixture();
+        let before = contracts.current();
+
+        // A bad contract address fails after the cache section parsed fine
+        file.write(&config_with(1, "[contracts]\ndead_pool = \"0x1234\"\n"));
+        assert!(reloader.reload().await.is_err());
+        file.write("[cache]\npositions_max_capacity = 0\n");
+        assert!(reloader.reload().await.is_err());
+        file.write("not toml");
+        assert!(reloader.reload().await.is_err());
+
+        assert_eq!(cache.position_ttl(), Some(Duration::from_secs(5)));
+        assert!(Arc::ptr_eq(&before, &contracts.current()));
+        let in_effect = reloader.subscribe().borrow().cache.clone();
+        assert_eq!(
+            in_effect,
+            CacheSettings {
+                positions_ttl_ms: 5000,
+                ..in_effect.clone()
+            }
+        );
+    }
+}
@@ -3,14 +3,20 @@
 //! All settings have sensible defaults and can be overridden via
 //! environment variables or configuration files.
 
+use std::collections::HashMap;
 use std::path::Path;
 use std::time::Duration;
 
-use config::{Config, ConfigError, Environment, File};
+use config::builder::DefaultState;
+use config::{Config, ConfigBuilder, ConfigError, Environment, File};
 use serde::Deserialize;
 
+use crate::indexer::{Contract, EventKind};
+use crate::store::RetryPolicy;
+use crate::streaming::{Topic, wire};
+
 /// Root configuration structure.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
 pub struct Settings {
     /// Ethereum RPC configuration.
     pub rpc: RpcSettings,
@@ -22,12 +28,63 @@ pub struct Settings {
     pub api: ApiSettings,
     /// In-memory cache configuration.
     pub cache: CacheSettings,
+    /// Aggregate statistics configuration.
+    #[serde(default)]
+    pub stats: StatsSettings,
+    /// Leaderboard refresh configuration.
+    #[serde(default)]
+    pub leaderboard: LeaderboardSettings,
+    /// Token flow analytics configuration.
+    #[serde(default)]
+    pub token_flows: TokenFlowSettings,
+    /// Transaction context enrichment configuration.
+    #[serde(default)]
+    pub tx_context: TxContextSettings,
+    /// Raw log archive configuration.
+    #[serde(default)]
+    pub raw_logs: RawLogSettings,
+    /// Next-scan prediction configuration.
+    #[serde(default)]
+    pub scan_prediction: ScanPredictionSettings,
+    /// User profile document configuration.
+    #[serde(default)]
+    pub user_profiles: UserProfileSettings,
+    /// Event outbox configuration.
+    #[serde(default)]
+    pub outbox: OutboxSettings,
+    /// DeadPool round watcher configuration.
+    #[serde(default)]
+    pub round_watcher: RoundWatcherSettings,
+    /// DATA holder balance configuration.
+    #[serde(default)]
+    pub holders: HolderSettings,
+    /// Boost expiry tracking configuration.
+    #[serde(default)]
+    pub boosts: BoostSettings,
+    /// Poison event quarantine configuration.
+    #[serde(default)]
+    pub quarantine: QuarantineSettings,
+    /// Wire schema versions published per topic.
+    #[serde(default)]
+    pub schemas: SchemaSettings,
+    /// Graceful shutdown configuration.
+    #[serde(default)]
+    pub shutdown: ShutdownSettings,
     /// Logging configuration.
     pub logging: LoggingSettings,
     /// Metrics configuration.
     pub metrics: MetricsSettings,
     /// Smart contract addresses.
     pub contracts: ContractAddresses,
+    /// Chains indexed side by side, each with its own endpoints, contracts,
+    /// database schema and Iggy stream. Without any, the chain of `rpc` and
+    /// `contracts` is indexed.
+    #[serde(default)]
+    pub chains: Vec<ChainSettings>,
+    /// Chain served by the API routes outside `/chains/:chain_id` [default:
+    /// the first chain indexed].
+    #[serde(default)]
+    pub primary_chain: Option<u64>,
 }
 
 impl Settings {
@@ -46,8 +103,57 @@ impl Settings {
     pub fn load(environment: &str) -> Result<Self, ConfigError> {
         let config_dir = std::env::var("CONFIG_DIR").unwrap_or_else(|_| "config".into());
 
-        let builder = Config::builder()
-            // Start with default values
+        let builder = Self::defaults()?
+            // Load default configuration file
+            .add_source(File::with_name(&format!("{config_dir}/default")).required(false))
+            // Load environment-specific file
+            .add_source(File::with_name(&format!("{config_dir}/{environment}")).required(false))
+            // Override with environment variables (INDEXER_ prefix)
+            .add_source(
+                Environment::with_prefix("INDEXER")
+                    .separator("__")
+                    .try_parsing(true),
+            );
+
+        builder.build()?.try_deserialize()
+    }
+
+    /// Load settings from a specific file path.
+    ///
+    /// # Errors
+    /// Returns `ConfigError` if the file cannot be read or parsed.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, ConfigError> {
+        Config::builder()
+            .add_source(File::from(path.as_ref()))
+            .build()?
+            .try_deserialize()
+    }
+
+    /// Load settings from a file layered over the built-in defaults, so the
+    /// file only needs the values it changes.
+    ///
+    /// # Errors
+    /// Returns `ConfigError` if the file cannot be read or parsed.
+    pub fn from_file_with_defaults<P: AsRef<Path>>(path: P) -> Result<Self, ConfigError> {
+        Self::defaults()?
+            .add_source(File::from(path.as_ref()))
+            .build()?
+            .try_deserialize()
+    }
+
+    /// Builder holding the built-in default values.
+    fn defaults() -> Result<ConfigBuilder<DefaultState>, ConfigError> {
+        let builder = Self::connection_defaults(Config::builder())?;
+        let builder = Self::api_defaults(builder)?;
+        let builder = Self::indexing_defaults(builder)?;
+        Self::service_defaults(builder)
+    }
+
+    /// Defaults of the `rpc`, `database` and `iggy` connections.
+    fn connection_defaults(
+        builder: ConfigBuilder<DefaultState>,
+    ) -> Result<ConfigBuilder<DefaultState>, ConfigError> {
+        builder
             .set_default("rpc.url", "http://localhost:8545")?
             .set_default("rpc.ws_url", "ws://localhost:8546")?
             .set_default("rpc.chain_id", 1)?
@@ -61,26 +167,104 @@ impl Settings {
             .set_default("database.min_connections", 1)?
             .set_default("database.connect_timeout_ms", 5000)?
             .set_default("database.idle_timeout_ms", 600_000)?
+            .set_default("database.batch_writes", true)?
+            .set_default("database.batch_window_ms", 0)?
+            .set_default("database.statement_timeout_ms", 30_000)?
+            .set_default("database.application_name", "ghostnet-indexer")?
+            .set_default("database.max_retries", 3)?
+            .set_default("database.retry_delay_ms", 100)?
             .set_default("iggy.url", "tcp://localhost:8090")?
             .set_default("iggy.stream_name", "ghostnet")?
             .set_default("iggy.partition_count", 3)?
             .set_default("iggy.replication_factor", 1)?
             .set_default("iggy.username", "iggy")?
             .set_default("iggy.password", "iggy")?
+            .set_default("iggy.max_batch_size", 500)?
+            .set_default("iggy.max_batch_delay_ms", 50)?
+            .set_default("iggy.max_retries", 5)?
+            .set_default("iggy.retry_backoff_ms", 100)?
+            .set_default("iggy.dead_letter_topic", "dead-letter")?
+            .set_default("iggy.health_max_buffered", 10_000)?
+            .set_default("iggy.health_max_dead_lettered", 100)
+    }
+
+    /// Defaults of `api` and the `cache` behind it.
+    fn api_defaults(
+        builder: ConfigBuilder<DefaultState>,
+    ) -> Result<ConfigBuilder<DefaultState>, ConfigError> {
+        builder
             .set_default("api.host", "0.0.0.0")?
             .set_default("api.port", 8080)?
             .set_default("api.cors_origins", vec!["http://localhost:5173"])?
             .set_default("api.request_timeout_ms", 30000)?
+            .set_default("api.chain_read_timeout_ms", 2000)?
             .set_default("api.websocket.max_connections", 10000)?
             .set_default("api.websocket.ping_interval_ms", 30000)?
             .set_default("api.websocket.pong_timeout_ms", 10000)?
-            .set_default("api.rate_limit.requests_per_second", 100)?
-            .set_default("api.rate_limit.burst_size", 200)?
+            .set_default("api.websocket.replay_retention_secs", 3600)?
+            .set_default("api.websocket.replay_max_events", 10_000)?
+            .set_default("api.rate_limit.requests_per_minute", 600)?
+            .set_default("api.rate_limit.burst_size", 50)?
+            .set_default("api.rate_limit.websocket_connects_per_minute", 1200)?
+            .set_default("api.rate_limit.subscribe_messages_per_minute", 120)?
             .set_default("cache.positions_ttl_ms", 5000)?
             .set_default("cache.positions_max_capacity", 100_000)?
             .set_default("cache.leaderboard_ttl_ms", 60000)?
             .set_default("cache.leaderboard_max_capacity", 1000)?
-            .set_default("cache.stats_ttl_ms", 10000)?
+            .set_default("cache.stats_ttl_ms", 10000)
+    }
+
+    /// Defaults of what the indexer derives and keeps, `stats` to `quarantine`.
+    fn indexing_defaults(
+        builder: ConfigBuilder<DefaultState>,
+    ) -> Result<ConfigBuilder<DefaultState>, ConfigError> {
+        builder
+            .set_default("stats.flush_interval_ms", 1000)?
+            .set_default("stats.flush_threshold", 256)?
+            .set_default("leaderboard.refresh_interval_ms", 30000)?
+            .set_default("leaderboard.default_limit", 100)?
+            .set_default("leaderboard.max_entries", 500)?
+            .set_default("token_flows.persist_transfers", false)?
+            .set_default("token_flows.default_window_secs", 86_400)?
+            .set_default("tx_context.enabled", false)?
+            .set_default("tx_context.cache_capacity", 10_000)?
+            .set_default("raw_logs.enabled", true)?
+            .set_default("scan_prediction.history", 20)?
+            .set_default("scan_prediction.cache_ttl_ms", 5000)?
+            .set_default("scan_prediction.use_contract", true)?
+            .set_default("user_profiles.cache_ttl_ms", 30_000)?
+            .set_default("user_profiles.section_timeout_ms", 2000)?
+            .set_default("user_profiles.history_limit", 1000)?
+            .set_default("user_profiles.bets_limit", 1000)?
+            .set_default("user_profiles.flow_window_secs", 2_592_000)?
+            .set_default("outbox.enabled", true)?
+            .set_default("outbox.poll_interval_ms", 500)?
+            .set_default("outbox.batch_size", 500)?
+            .set_default("outbox.retry_backoff_ms", 1000)?
+            .set_default("outbox.max_retry_backoff_ms", 60_000)?
+            .set_default("outbox.retention_hours", 24)?
+            .set_default("round_watcher.enabled", true)?
+            .set_default("round_watcher.poll_interval_ms", 5000)?
+            .set_default("round_watcher.max_rounds", 500)?
+            .set_default("holders.enabled", true)?
+            .set_default("holders.reconcile", true)?
+            .set_default("holders.reconcile_interval_secs", 3600)?
+            .set_default("holders.reconcile_sample_size", 100)?
+            .set_default("boosts.enabled", true)?
+            .set_default("boosts.poll_interval_ms", 5000)?
+            .set_default("boosts.max_boosts", 500)?
+            .set_default("quarantine.enabled", true)?
+            .set_default("quarantine.max_retries", 3)?
+            .set_default("quarantine.retry_delay_ms", 100)?
+            .set_default("quarantine.strict_ordering", vec!["position"])?
+            .set_default("quarantine.warn_depth", 100)
+    }
+
+    /// Defaults of `logging`, `metrics` and the `contracts`.
+    fn service_defaults(
+        builder: ConfigBuilder<DefaultState>,
+    ) -> Result<ConfigBuilder<DefaultState>, ConfigError> {
+        builder
             .set_default("logging.level", "info")?
             .set_default("logging.format", "json")?
             .set_default("logging.file_path", Option::<String>::None)?
@@ -111,30 +295,59 @@ impl Settings {
             .set_default(
                 "contracts.rewards_distributor",
                 "0x0000000000000000000000000000000000000006",
-            )?
-            // Load default configuration file
-            .add_source(File::with_name(&format!("{config_dir}/default")).required(false))
-            // Load environment-specific file
-            .add_source(File::with_name(&format!("{config_dir}/{environment}")).required(false))
-            // Override with environment variables (INDEXER_ prefix)
-            .add_source(
-                Environment::with_prefix("INDEXER")
-                    .separator("__")
-                    .try_parsing(true),
-            );
+            )
+    }
 
-        builder.build()?.try_deserialize()
+    /// Chains to index: the enabled entries of `chains`, or without any the
+    /// chain of `rpc` and `contracts`, whose tables are in the `public`
+    /// schema.
+    #[must_use]
+    pub fn indexed_chains(&self) -> Vec<IndexedChain> {
+        if self.chains.is_empty() {
+            return vec![IndexedChain {
+                chain_id: self.rpc.chain_id,
+                rpc: self.rpc.clone(),
+                contracts: self.contracts.clone(),
+                schema: DEFAULT_SCHEMA.into(),
+                stream_name: self.iggy.stream_name.clone(),
+            }];
+        }
+        self.chains
+            .iter()
+            .filter(|chain| chain.enabled)
+            .map(|chain| chain.resolve(&self.rpc, &self.iggy))
+            .collect()
     }
 
-    /// Load settings from a specific file path.
-    ///
-    /// # Errors
-    /// Returns `ConfigError` if the file cannot be read or parsed.
-    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, ConfigError> {
-        Config::builder()
-            .add_source(File::from(path.as_ref()))
-            .build()?
-            .try_deserialize()
+    /// Chain served by the API routes outside `/chains/:chain_id`.
+    #[must_use]
+    pub fn primary_chain_id(&self) -> Option<u64> {
+        self.primary_chain
+            .or_else(|| self.indexed_chains().first().map(|chain| chain.chain_id))
+    }
+
+    /// The indexed chain `chain_id`, or the primary chain without one.
+    #[must_use]
+    pub fn indexed_chain(&self, chain_id: Option<u64>) -> Option<IndexedChain> {
+        let chain_id = chain_id.or_else(|| self.primary_chain_id())?;
+        self.indexed_chains()
+            .into_iter()
+            .find(|chain| chain.chain_id == chain_id)
+    }
+
+    /// Settings of `chain` alone: its endpoints and contracts as `rpc` and
+    /// `contracts`, and its stream as `iggy.stream_name`.
+    #[must_use]
+    pub fn for_chain(&self, chain: &IndexedChain) -> Self {
+        Self {
+            rpc: chain.rpc.clone(),
+            contracts: chain.contracts.clone(),
+            iggy: IggySettings {
+                stream_name: chain.stream_name.clone(),
+                ..self.iggy.clone()
+            },
+            ..self.clone()
+        }
     }
 
     /// Validate settings and return any validation errors.
@@ -165,13 +378,40 @@ impl Settings {
         if self.database.min_connections > self.database.max_connections {
             errors.push("database.min_connections cannot exceed max_connections".into());
         }
+        // Postgres silently truncates longer names (NAMEDATALEN)
+        if self.database.application_name.len() > 63 {
+            errors.push("database.application_name cannot exceed 63 bytes".into());
+        }
 
         // API validation
         if self.api.port == 0 {
             errors.push("api.port must be non-zero".into());
         }
-        if self.api.rate_limit.requests_per_second == 0 {
-            errors.push("api.rate_limit.requests_per_second must be non-zero".into());
+        if self.api.chain_read_timeout_ms == 0 {
+            errors.push("api.chain_read_timeout_ms must be non-zero".into());
+        }
+        let rate_limit = &self.api.rate_limit;
+        if rate_limit.requests_per_minute == 0 {
+            errors.push("api.rate_limit.requests_per_minute must be non-zero".into());
+        }
+        if rate_limit.burst_size == 0 {
+            errors.push("api.rate_limit.burst_size must be non-zero".into());
+        }
+        if rate_limit.websocket_connects_per_minute == 0
+            || rate_limit.subscribe_messages_per_minute == 0
+        {
+            errors.push("api.rate_limit WebSocket limits must be non-zero".into());
+        }
+        if self.api.websocket.replay_max_events == 0 {
+            errors.push("api.websocket.replay_max_events must be non-zero".into());
+        }
+
+        // Iggy validation
+        if self.iggy.max_batch_size == 0 {
+            errors.push("iggy.max_batch_size must be non-zero".into());
+        }
+        if self.iggy.max_batch_delay_ms == 0 {
+            errors.push("iggy.max_batch_delay_ms must be non-zero".into());
         }
 
         // Cache validation
@@ -179,16 +419,202 @@ impl Settings {
             errors.push("cache.positions_max_capacity must be non-zero".into());
         }
 
+        // Stats validation
+        if self.stats.flush_interval_ms == 0 {
+            errors.push("stats.flush_interval_ms must be non-zero".into());
+        }
+        if self.stats.flush_threshold == 0 {
+            errors.push("stats.flush_threshold must be non-zero".into());
+        }
+
+        // Leaderboard validation
+        if self.leaderboard.refresh_interval_ms == 0 {
+            errors.push("leaderboard.refresh_interval_ms must be non-zero".into());
+        }
+        if self.leaderboard.default_limit == 0 {
+            errors.push("leaderboard.default_limit must be non-zero".into());
+        }
+        if self.leaderboard.default_limit > self.leaderboard.max_entries {
+            errors.push("leaderboard.default_limit cannot exceed max_entries".into());
+        }
+
+        // Token flow validation
+        if self.token_flows.default_window_secs == 0 {
+            errors.push("token_flows.default_window_secs must be non-zero".into());
+        }
+
+        // Transaction context validation
+        if self.tx_context.enabled && self.tx_context.cache_capacity == 0 {
+            errors.push("tx_context.cache_capacity must be non-zero".into());
+        }
+        if self.scan_prediction.history < 2 {
+            errors.push("scan_prediction.history must be at least 2".into());
+        }
+        if self.user_profiles.section_timeout_ms == 0 {
+            errors.push("user_profiles.section_timeout_ms must be non-zero".into());
+        }
+        if self.user_profiles.history_limit == 0 || self.user_profiles.bets_limit == 0 {
+            errors.push("user_profiles.history_limit and bets_limit must be non-zero".into());
+        }
+        if self.user_profiles.flow_window_secs == 0 {
+            errors.push("user_profiles.flow_window_secs must be non-zero".into());
+        }
+
+        self.validate_publishing(&mut errors);
+
+        // Shutdown validation
+        if self.shutdown.grace_period_ms == 0 {
+            errors.push("shutdown.grace_period_ms must be non-zero".into());
+        }
+
+        // Contract validation
+        validate_contract_names("contracts", &self.contracts, &mut errors);
+        self.validate_chains(&mut errors);
+
         if errors.is_empty() {
             Ok(())
         } else {
             Err(errors)
         }
     }
+
+    /// Validate the chains and the primary chain.
+    fn validate_chains(&self, errors: &mut Vec<String>) {
+        let mut chain_ids = Vec::new();
+        for chain in &self.chains {
+            let id = chain.chain_id;
+            if id == 0 {
+                errors.push("chains: chain_id must be non-zero".into());
+            }
+            if chain_ids.contains(&id) {
+                errors.push(format!("chains: chain {id} is listed more than once"));
+            }
+            chain_ids.push(id);
+            if chain.url.is_empty() {
+                errors.push(format!("chains.{id}.url cannot be empty"));
+            }
+            if let Some(schema) = &chain.schema
+                && !is_schema_name(schema)
+            {
+                errors.push(format!(
+                    "chains.{id}.schema must be a lowercase identifier of at most 63 bytes"
+                ));
+            }
+            validate_contract_names(&format!("chains.{id}.contracts"), &chain.contracts, errors);
+        }
+
+        let indexed = self.indexed_chains();
+        if !self.chains.is_empty() && indexed.is_empty() {
+            errors.push("chains: no chain is enabled".into());
+        }
+        // Chains sharing a schema or a stream would mix their data
+        for (i, chain) in indexed.iter().enumerate() {
+            let earlier = &indexed[..i];
+            let (schema, stream) = (&chain.schema, &chain.stream_name);
+            if earlier.iter().any(|other| &other.schema == schema) {
+                errors.push(format!("chains: schema '{schema}' is used twice"));
+            }
+            if earlier.iter().any(|other| &other.stream_name == stream) {
+                errors.push(format!("chains: stream '{stream}' is used twice"));
+            }
+        }
+        if let Some(primary) = self.primary_chain
+            && !indexed.iter().any(|chain| chain.chain_id == primary)
+        {
+            errors.push(format!("primary_chain {primary} is not an indexed chain"));
+        }
+    }
+
+    /// Validate the outbox and round watcher settings.
+    fn validate_publishing(&self, errors: &mut Vec<String>) {
+        // Outbox validation
+        let outbox = &self.outbox;
+        if outbox.enabled {
+            if outbox.poll_interval_ms == 0 {
+                errors.push("outbox.poll_interval_ms must be non-zero".into());
+            }
+            if outbox.batch_size == 0 {
+                errors.push("outbox.batch_size must be non-zero".into());
+            }
+            if outbox.retry_backoff_ms == 0 {
+                errors.push("outbox.retry_backoff_ms must be non-zero".into());
+            }
+            if outbox.retry_backoff_ms > outbox.max_retry_backoff_ms {
+                errors.push("outbox.retry_backoff_ms cannot exceed max_retry_backoff_ms".into());
+            }
+        }
+
+        // Round watcher validation
+        let watcher = &self.round_watcher;
+        if watcher.enabled {
+            if watcher.poll_interval_ms == 0 {
+                errors.push("round_watcher.poll_interval_ms must be non-zero".into());
+            }
+            if watcher.max_rounds == 0 {
+                errors.push("round_watcher.max_rounds must be non-zero".into());
+            }
+            if watcher.closing_soon_secs.contains(&0) {
+                errors.push("round_watcher.closing_soon_secs must be non-zero".into());
+            }
+        }
+
+        // Holder reconciliation validation
+        let holders = &self.holders;
+        if holders.enabled && holders.reconcile {
+            if holders.reconcile_interval_secs == 0 {
+                errors.push("holders.reconcile_interval_secs must be non-zero".into());
+            }
+            if holders.reconcile_sample_size == 0 {
+                errors.push("holders.reconcile_sample_size must be non-zero".into());
+            }
+        }
+
+        // Boost expiry validation
+        let boosts = &self.boosts;
+        if boosts.enabled {
+            if boosts.poll_interval_ms == 0 {
+                errors.push("boosts.poll_interval_ms must be non-zero".into());
+            }
+            if boosts.max_boosts == 0 {
+                errors.push("boosts.max_boosts must be non-zero".into());
+            }
+        }
+
+        // Quarantine validation
+        let quarantine = &self.quarantine;
+        for name in &quarantine.strict_ordering {
+            if !EventKind::ALL.iter().any(|kind| kind.handler() == name) {
+                errors.push(format!(
+                    "quarantine.strict_ordering: unknown handler '{name}'"
+                ));
+            }
+        }
+        if quarantine.enabled && quarantine.warn_depth == 0 {
+            errors.push("quarantine.warn_depth must be non-zero".into());
+        }
+
+        // Wire schema validation
+        for (name, versions) in &self.schemas.versions {
+            let Some(topic) = Topic::from_name(name) else {
+                errors.push(format!("schemas.versions: unknown topic '{name}'"));
+                continue;
+            };
+            if versions.is_empty() {
+                errors.push(format!("schemas.versions.{name} cannot be empty"));
+            }
+            for version in versions {
+                if !wire::schema_versions(topic).contains(version) {
+                    errors.push(format!(
+                        "schemas.versions.{name}: unsupported schema version {version}"
+                    ));
+                }
+            }
+        }
+    }
 }
 
 /// Ethereum RPC configuration.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
 pub struct RpcSettings {
     /// HTTP RPC endpoint URL.
     pub url: String,
@@ -228,8 +654,108 @@ impl RpcSettings {
     }
 }
 
+/// A chain indexed alongside others, an entry of `[[chains]]`.
+///
+/// Polling, retries and batch sizes are those of `[rpc]`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct ChainSettings {
+    /// Chain ID, e.g. 4326 for MegaETH mainnet.
+    pub chain_id: u64,
+    /// Whether the chain is indexed.
+    #[serde(default = "default_chain_enabled")]
+    pub enabled: bool,
+    /// HTTP RPC endpoint URL.
+    pub url: String,
+    /// WebSocket RPC endpoint URL (for subscriptions).
+    #[serde(default)]
+    pub ws_url: String,
+    /// Database schema holding the chain's tables [default:
+    /// `chain_<chain_id>`]. Set `public` for the chain indexed before
+    /// `[[chains]]` was configured, to keep its tables.
+    #[serde(default)]
+    pub schema: Option<String>,
+    /// Iggy stream the chain's events are published to [default:
+    /// `<iggy.stream_name>-<chain_id>`].
+    #[serde(default)]
+    pub stream_name: Option<String>,
+    /// Contract addresses on the chain.
+    pub contracts: ContractAddresses,
+}
+
+impl ChainSettings {
+    /// The chain to index, with the rest of its RPC settings from `rpc`.
+    fn resolve(&self, rpc: &RpcSettings, iggy: &IggySettings) -> IndexedChain {
+        IndexedChain {
+            chain_id: self.chain_id,
+            rpc: RpcSettings {
+                url: self.url.clone(),
+                ws_url: self.ws_url.clone(),
+                chain_id: self.chain_id,
+                ..rpc.clone()
+            },
+            contracts: self.contracts.clone(),
+            schema: self
+                .schema
+                .clone()
+                .unwrap_or_else(|| format!("chain_{}", self.chain_id)),
+            stream_name: self
+                .stream_name
+                .clone()
+                .unwrap_or_else(|| format!("{}-{}", iggy.stream_name, self.chain_id)),
+        }
+    }
+}
+
+const fn default_chain_enabled() -> bool {
+    true
+}
+
+/// A chain to index, see [`Settings::indexed_chains`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexedChain {
+    /// Chain ID.
+    pub chain_id: u64,
+    /// RPC settings, with the chain's endpoints.
+    pub rpc: RpcSettings,
+    /// Contract addresses on the chain.
+    pub contracts: ContractAddresses,
+    /// Database schema holding the chain's tables.
+    pub schema: String,
+    /// Iggy stream the chain's events are published to.
+    pub stream_name: String,
+}
+
+/// Schema of the chain of `rpc` and `contracts`.
+const DEFAULT_SCHEMA: &str = "public";
+
+/// Check that `name` is a schema name usable unquoted: lowercase letters,
+/// digits and `_`, not starting with a digit, within Postgres' 63 bytes.
+fn is_schema_name(name: &str) -> bool {
+    let starts_with_digit = name.starts_with(|c: char| c.is_ascii_digit());
+    !name.is_empty()
+        && name.len() <= 63
+        && !starts_with_digit
+        && name
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'_')
+}
+
+/// Check the contract names in the lists of `contracts`, reporting unknown
+/// ones under `section`.
+fn validate_contract_names(section: &str, contracts: &ContractAddresses, errors: &mut Vec<String>) {
+    let names = contracts
+        .disabled
+        .iter()
+        .chain(contracts.code_hashes.keys());
+    for name in names.chain(contracts.additional.keys()) {
+        if name.parse::<Contract>().is_err() {
+            errors.push(format!("{section}: unknown contract '{name}'"));
+        }
+    }
+}
+
 /// Database configuration.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
 pub struct DatabaseSettings {
     /// `PostgreSQL` connection URL.
     pub url: String,
@@ -241,6 +767,22 @@ pub struct DatabaseSettings {
     pub connect_timeout_ms: u64,
     /// Idle connection timeout in milliseconds.
     pub idle_timeout_ms: u64,
+    /// Write each pipeline's handler writes and checkpoint as one transaction
+    /// per block, instead of a transaction per write.
+    pub batch_writes: bool,
+    /// Blocks completing within this many milliseconds of a batch's first
+    /// write join it (0 commits every block). Suits the realtime path, where
+    /// mini-blocks arrive every few milliseconds.
+    pub batch_window_ms: u64,
+    /// Server-side limit on each statement, in milliseconds (0 is
+    /// unlimited).
+    pub statement_timeout_ms: u64,
+    /// Name the connections report in `pg_stat_activity`.
+    pub application_name: String,
+    /// Retries of acquiring a connection after a connection error.
+    pub max_retries: u32,
+    /// Delay before the first retry in milliseconds, doubling after each.
+    pub retry_delay_ms: u64,
 }
 
 impl DatabaseSettings {
@@ -255,10 +797,32 @@ impl DatabaseSettings {
     pub const fn idle_timeout(&self) -> Duration {
         Duration::from_millis(self.idle_timeout_ms)
     }
+
+    /// Get the statement timeout as a `Duration`.
+    #[must_use]
+    pub const fn statement_timeout(&self) -> Duration {
+        Duration::from_millis(self.statement_timeout_ms)
+    }
+
+    /// Get the policy for retrying connection errors.
+    #[must_use]
+    pub const fn retry_policy(&self) -> RetryPolicy {
+        RetryPolicy::new(self.max_retries, Duration::from_millis(self.retry_delay_ms))
+    }
+
+    /// Get the write batch window, if writes are batched.
+    #[must_use]
+    pub const fn batch_window(&self) -> Option<Duration> {
+        if self.batch_writes {
+            Some(Duration::from_millis(self.batch_window_ms))
+        } else {
+            None
+        }
+    }
 }
 
 /// Apache Iggy streaming configuration.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
 pub struct IggySettings {
     /// Iggy server URL.
     pub url: String,
@@ -272,10 +836,80 @@ pub struct IggySettings {
     pub username: String,
     /// Password for authentication.
     pub password: String,
+    /// Maximum number of messages buffered before a batch is sent.
+    #[serde(default = "default_iggy_max_batch_size")]
+    pub max_batch_size: usize,
+    /// Maximum time a message may wait in the buffer, in milliseconds.
+    #[serde(default = "default_iggy_max_batch_delay_ms")]
+    pub max_batch_delay_ms: u64,
+    /// Retry attempts for a failed batch before it is dead-lettered.
+    #[serde(default = "default_iggy_max_retries")]
+    pub max_retries: u32,
+    /// Initial retry backoff in milliseconds (doubles on each attempt).
+    #[serde(default = "default_iggy_retry_backoff_ms")]
+    pub retry_backoff_ms: u64,
+    /// Local JSONL file for dead-lettered batches.
+    ///
+    /// When unset, dead-lettered batches are published to `dead_letter_topic`.
+    #[serde(default)]
+    pub dead_letter_path: Option<String>,
+    /// Topic for dead-lettered batches when no `dead_letter_path` is set.
+    #[serde(default = "default_iggy_dead_letter_topic")]
+    pub dead_letter_topic: String,
+    /// Buffered messages past which `GET /health` reports the publisher
+    /// degraded.
+    #[serde(default = "default_iggy_health_max_buffered")]
+    pub health_max_buffered: usize,
+    /// Messages dead-lettered since startup past which `GET /health`
+    /// reports the publisher degraded.
+    #[serde(default = "default_iggy_health_max_dead_lettered")]
+    pub health_max_dead_lettered: u64,
+}
+
+impl IggySettings {
+    /// Get the maximum batch delay as a `Duration`.
+    #[must_use]
+    pub const fn max_batch_delay(&self) -> Duration {
+        Duration::from_millis(self.max_batch_delay_ms)
+    }
+
+    /// Get the initial retry backoff as a `Duration`.
+    #[must_use]
+    pub const fn retry_backoff(&self) -> Duration {
+        Duration::from_millis(self.retry_backoff_ms)
+    }
+}
+
+const fn default_iggy_max_batch_size() -> usize {
+    500
+}
+
+const fn default_iggy_max_batch_delay_ms() -> u64 {
+    50
+}
+
+const fn default_iggy_max_retries() -> u32 {
+    5
+}
+
+const fn default_iggy_retry_backoff_ms() -> u64 {
+    100
+}
+
+fn default_iggy_dead_letter_topic() -> String {
+    "dead-letter".into()
+}
+
+const fn default_iggy_health_max_buffered() -> usize {
+    10_000
+}
+
+const fn default_iggy_health_max_dead_lettered() -> u64 {
+    100
 }
 
 /// API server configuration.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
 pub struct ApiSettings {
     /// Host to bind to.
     pub host: String,
@@ -285,6 +919,8 @@ pub struct ApiSettings {
     pub cors_origins: Vec<String>,
     /// Request timeout in milliseconds.
     pub request_timeout_ms: u64,
+    /// Budget of a contract read made for `GET /chain/read`, in milliseconds.
+    pub chain_read_timeout_ms: u64,
     /// WebSocket settings.
     pub websocket: WebSocketSettings,
     /// Rate limiting settings.
@@ -298,6 +934,12 @@ impl ApiSettings {
         Duration::from_millis(self.request_timeout_ms)
     }
 
+    /// Get the contract read budget as a `Duration`.
+    #[must_use]
+    pub const fn chain_read_timeout(&self) -> Duration {
+        Duration::from_millis(self.chain_read_timeout_ms)
+    }
+
     /// Get the socket address string.
     #[must_use]
     pub fn socket_addr(&self) -> String {
@@ -306,7 +948,7 @@ impl ApiSettings {
 }
 
 /// WebSocket configuration.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
 pub struct WebSocketSettings {
     /// Maximum concurrent WebSocket connections.
     pub max_connections: usize,
@@ -314,6 +956,11 @@ pub struct WebSocketSettings {
     pub ping_interval_ms: u64,
     /// Pong timeout in milliseconds.
     pub pong_timeout_ms: u64,
+    /// How long published events are kept for clients resuming with
+    /// `from_sequence`, in seconds.
+    pub replay_retention_secs: u64,
+    /// Most published events kept per topic for resuming clients.
+    pub replay_max_events: u64,
 }
 
 impl WebSocketSettings {
@@ -328,19 +975,37 @@ impl WebSocketSettings {
     pub const fn pong_timeout(&self) -> Duration {
         Duration::from_millis(self.pong_timeout_ms)
     }
+
+    /// Get the replay retention as a `Duration`.
+    #[must_use]
+    pub const fn replay_retention(&self) -> Duration {
+        Duration::from_secs(self.replay_retention_secs)
+    }
 }
 
 /// Rate limiting configuration.
-#[derive(Debug, Clone, Deserialize)]
+///
+/// Limits apply per client IP. Requests from loopback addresses and to the
+/// health endpoint are never limited.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
 pub struct RateLimitSettings {
-    /// Maximum requests per second per client.
-    pub requests_per_second: u32,
-    /// Burst size (allows temporary spikes).
+    /// Maximum requests per minute per client.
+    pub requests_per_minute: u32,
+    /// Maximum requests per client within a single second.
     pub burst_size: u32,
+    /// Maximum WebSocket connection attempts per minute per client.
+    pub websocket_connects_per_minute: u32,
+    /// Maximum subscribe messages per minute per WebSocket connection.
+    pub subscribe_messages_per_minute: u32,
+    /// Header carrying the client IP set by a trusted reverse proxy
+    /// (e.g. `X-Forwarded-For`). Unset, the peer address is the client and
+    /// forwarding headers are ignored, since anyone can send them.
+    #[serde(default)]
+    pub trusted_proxy_header: Option<String>,
 }
 
 /// In-memory cache configuration.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
 pub struct CacheSettings {
     /// TTL for position cache entries in milliseconds.
     pub positions_ttl_ms: u64,
@@ -374,71 +1039,738 @@ impl CacheSettings {
     }
 }
 
-/// Logging configuration.
-#[derive(Debug, Clone, Deserialize)]
-pub struct LoggingSettings {
-    /// Log level (trace, debug, info, warn, error).
-    pub level: String,
-    /// Log format (json, pretty).
-    pub format: String,
-    /// Optional file path for log output.
-    pub file_path: Option<String>,
+/// Aggregate statistics configuration.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct StatsSettings {
+    /// Interval between stats flushes to the database, in milliseconds.
+    #[serde(default = "default_stats_flush_interval_ms")]
+    pub flush_interval_ms: u64,
+    /// Number of buffered deltas that triggers an early flush.
+    #[serde(default = "default_stats_flush_threshold")]
+    pub flush_threshold: usize,
 }
 
-/// Metrics configuration.
-#[derive(Debug, Clone, Deserialize)]
-pub struct MetricsSettings {
-    /// Whether metrics are enabled.
-    pub enabled: bool,
-    /// Host to bind metrics server to.
-    pub host: String,
-    /// Port for metrics server.
-    pub port: u16,
+impl StatsSettings {
+    /// Get the flush interval as a `Duration`.
+    #[must_use]
+    pub const fn flush_interval(&self) -> Duration {
+        Duration::from_millis(self.flush_interval_ms)
+    }
 }
 
-impl MetricsSettings {
-    /// Get the metrics socket address string.
-    #[must_use]
-    pub fn socket_addr(&self) -> String {
-        format!("{}:{}", self.host, self.port)
+impl Default for StatsSettings {
+    fn default() -> Self {
+        Self {
+            flush_interval_ms: default_stats_flush_interval_ms(),
+            flush_threshold: default_stats_flush_threshold(),
+        }
     }
 }
 
-/// GHOSTNET smart contract addresses.
-///
-/// These addresses point to the deployed contracts on MegaETH.
-/// All addresses should be checksummed.
-#[derive(Debug, Clone, Deserialize)]
-pub struct ContractAddresses {
-    /// GhostCore contract - main game logic.
-    pub ghost_core: String,
-    /// TraceScan contract - scan execution.
-    pub trace_scan: String,
-    /// DeadPool contract - prediction market.
-    pub dead_pool: String,
-    /// DataToken contract - $DATA ERC20.
-    pub data_token: String,
-    /// FeeRouter contract - fee collection.
-    pub fee_router: String,
-    /// RewardsDistributor contract - emissions.
-    pub rewards_distributor: String,
+const fn default_stats_flush_interval_ms() -> u64 {
+    1000
 }
 
-impl ContractAddresses {
-    /// Get all contract addresses as a vector.
-    ///
-    /// Useful for building log filters covering all contracts.
-    #[must_use]
-    pub fn all(&self) -> Vec<&str> {
-        vec![
-            &self.ghost_core,
-            &self.trace_scan,
-            &self.dead_pool,
-            &self.data_token,
-            &self.fee_router,
-            &self.rewards_distributor,
-        ]
-    }
+const fn default_stats_flush_threshold() -> usize {
+    256
+}
+
+/// Leaderboard refresh configuration.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct LeaderboardSettings {
+    /// Interval between leaderboard recomputations, in milliseconds.
+    #[serde(default = "default_leaderboard_refresh_interval_ms")]
+    pub refresh_interval_ms: u64,
+    /// Entries returned when a request doesn't specify a limit.
+    #[serde(default = "default_leaderboard_default_limit")]
+    pub default_limit: u32,
+    /// Entries computed and cached per leaderboard (upper bound for `limit`).
+    #[serde(default = "default_leaderboard_max_entries")]
+    pub max_entries: u32,
+}
+
+impl LeaderboardSettings {
+    /// Get the refresh interval as a `Duration`.
+    #[must_use]
+    pub const fn refresh_interval(&self) -> Duration {
+        Duration::from_millis(self.refresh_interval_ms)
+    }
+}
+
+impl Default for LeaderboardSettings {
+    fn default() -> Self {
+        Self {
+            refresh_interval_ms: default_leaderboard_refresh_interval_ms(),
+            default_limit: default_leaderboard_default_limit(),
+            max_entries: default_leaderboard_max_entries(),
+        }
+    }
+}
+
+const fn default_leaderboard_refresh_interval_ms() -> u64 {
+    30_000
+}
+
+const fn default_leaderboard_default_limit() -> u32 {
+    100
+}
+
+const fn default_leaderboard_max_entries() -> u32 {
+    500
+}
+
+/// Token flow analytics configuration.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct TokenFlowSettings {
+    /// Persist every raw transfer to `token_transfers`.
+    ///
+    /// Off by default because transfer volume is high. Hourly aggregates are
+    /// maintained regardless.
+    #[serde(default)]
+    pub persist_transfers: bool,
+    /// Window used by token stats queries that don't specify one, in seconds.
+    #[serde(default = "default_token_flows_window_secs")]
+    pub default_window_secs: u64,
+}
+
+impl TokenFlowSettings {
+    /// Get the default query window as a `Duration`.
+    #[must_use]
+    pub const fn default_window(&self) -> Duration {
+        Duration::from_secs(self.default_window_secs)
+    }
+}
+
+impl Default for TokenFlowSettings {
+    fn default() -> Self {
+        Self {
+            persist_transfers: false,
+            default_window_secs: default_token_flows_window_secs(),
+        }
+    }
+}
+
+/// Transaction context enrichment configuration.
+///
+/// When enabled, the indexer fetches the transaction behind each event and
+/// records which GHOSTNET function it called and who sent it. This costs an
+/// extra RPC request per transaction, so it is off by default.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct TxContextSettings {
+    /// Fetch and decode the transaction of each event.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Maximum number of transactions kept in the lookup cache.
+    #[serde(default = "default_tx_context_cache_capacity")]
+    pub cache_capacity: u64,
+}
+
+impl Default for TxContextSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            cache_capacity: default_tx_context_cache_capacity(),
+        }
+    }
+}
+
+const fn default_tx_context_cache_capacity() -> u64 {
+    10_000
+}
+
+/// Raw log archive configuration.
+///
+/// When enabled, the pipeline stores every log it receives in `raw_logs`
+/// before routing it, so `ghostnet-indexer replay` can run the handlers over
+/// a block range again without refetching it from RPC.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct RawLogSettings {
+    /// Archive logs before routing them.
+    #[serde(default = "default_raw_logs_enabled")]
+    pub enabled: bool,
+}
+
+impl Default for RawLogSettings {
+    fn default() -> Self {
+        Self {
+            enabled: default_raw_logs_enabled(),
+        }
+    }
+}
+
+const fn default_raw_logs_enabled() -> bool {
+    true
+}
+
+/// Next-scan prediction configuration.
+///
+/// Predictions come from `GhostCore.getLevelState` when `use_contract` is
+/// set, and otherwise (or when the call fails) from the intervals between
+/// the last `history` scans of a level.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct ScanPredictionSettings {
+    /// Recent scans per level the cadence is inferred from.
+    #[serde(default = "default_scan_prediction_history")]
+    pub history: u32,
+    /// TTL of cached predictions in milliseconds. A `ScanExecuted` event
+    /// invalidates its level's prediction early.
+    #[serde(default = "default_scan_prediction_cache_ttl_ms")]
+    pub cache_ttl_ms: u64,
+    /// Prefer the next scan time scheduled on-chain.
+    #[serde(default = "default_scan_prediction_use_contract")]
+    pub use_contract: bool,
+}
+
+impl ScanPredictionSettings {
+    /// Get the cache TTL as a `Duration`.
+    #[must_use]
+    pub const fn cache_ttl(&self) -> Duration {
+        Duration::from_millis(self.cache_ttl_ms)
+    }
+}
+
+impl Default for ScanPredictionSettings {
+    fn default() -> Self {
+        Self {
+            history: default_scan_prediction_history(),
+            cache_ttl_ms: default_scan_prediction_cache_ttl_ms(),
+            use_contract: default_scan_prediction_use_contract(),
+        }
+    }
+}
+
+const fn default_scan_prediction_history() -> u32 {
+    20
+}
+
+const fn default_scan_prediction_cache_ttl_ms() -> u64 {
+    5000
+}
+
+const fn default_scan_prediction_use_contract() -> bool {
+    true
+}
+
+/// User profile document configuration.
+///
+/// `GET /users/:address` reads each section of the document concurrently,
+/// giving each `section_timeout_ms` before leaving it out. Complete
+/// documents are cached for `cache_ttl_ms`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct UserProfileSettings {
+    /// TTL of cached profile documents in milliseconds.
+    #[serde(default = "default_user_profiles_cache_ttl_ms")]
+    pub cache_ttl_ms: u64,
+    /// How long each section may take before the document is returned
+    /// without it, in milliseconds.
+    #[serde(default = "default_user_profiles_section_timeout_ms")]
+    pub section_timeout_ms: u64,
+    /// Most recent position history entries the history summary covers.
+    #[serde(default = "default_user_profiles_history_limit")]
+    pub history_limit: u32,
+    /// Most recent bets the `DeadPool` summary covers.
+    #[serde(default = "default_user_profiles_bets_limit")]
+    pub bets_limit: u32,
+    /// Window of the token flow summary, in seconds.
+    #[serde(default = "default_user_profiles_flow_window_secs")]
+    pub flow_window_secs: u64,
+}
+
+impl UserProfileSettings {
+    /// Get the cache TTL as a `Duration`.
+    #[must_use]
+    pub const fn cache_ttl(&self) -> Duration {
+        Duration::from_millis(self.cache_ttl_ms)
+    }
+
+    /// Get the section timeout as a `Duration`.
+    #[must_use]
+    pub const fn section_timeout(&self) -> Duration {
+        Duration::from_millis(self.section_timeout_ms)
+    }
+
+    /// Get the token flow window as a `Duration`.
+    #[must_use]
+    pub const fn flow_window(&self) -> Duration {
+        Duration::from_secs(self.flow_window_secs)
+    }
+}
+
+impl Default for UserProfileSettings {
+    fn default() -> Self {
+        Self {
+            cache_ttl_ms: default_user_profiles_cache_ttl_ms(),
+            section_timeout_ms: default_user_profiles_section_timeout_ms(),
+            history_limit: default_user_profiles_history_limit(),
+            bets_limit: default_user_profiles_bets_limit(),
+            flow_window_secs: default_user_profiles_flow_window_secs(),
+        }
+    }
+}
+
+const fn default_user_profiles_cache_ttl_ms() -> u64 {
+    30_000
+}
+
+const fn default_user_profiles_section_timeout_ms() -> u64 {
+    2000
+}
+
+const fn default_user_profiles_history_limit() -> u32 {
+    1000
+}
+
+const fn default_user_profiles_bets_limit() -> u32 {
+    1000
+}
+
+const fn default_user_profiles_flow_window_secs() -> u64 {
+    2_592_000 // 30 days
+}
+
+/// Event outbox configuration.
+///
+/// Handlers write their events to the `event_outbox` table in the same
+/// transaction as the state change, and the outbox relay publishes them to
+/// Iggy. Disabled, no events are published.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct OutboxSettings {
+    /// Write events to the outbox and run the relay.
+    #[serde(default = "default_outbox_enabled")]
+    pub enabled: bool,
+    /// Interval between polls for unpublished events, in milliseconds.
+    #[serde(default = "default_outbox_poll_interval_ms")]
+    pub poll_interval_ms: u64,
+    /// Most events published per poll.
+    #[serde(default = "default_outbox_batch_size")]
+    pub batch_size: u32,
+    /// Delay before the first retry of a failed publish, in milliseconds.
+    /// Doubles with every further failure.
+    #[serde(default = "default_outbox_retry_backoff_ms")]
+    pub retry_backoff_ms: u64,
+    /// Upper bound for the retry delay, in milliseconds.
+    #[serde(default = "default_outbox_max_retry_backoff_ms")]
+    pub max_retry_backoff_ms: u64,
+    /// How long published events are kept before deletion, in hours.
+    #[serde(default = "default_outbox_retention_hours")]
+    pub retention_hours: u64,
+}
+
+impl OutboxSettings {
+    /// Get the poll interval as a `Duration`.
+    #[must_use]
+    pub const fn poll_interval(&self) -> Duration {
+        Duration::from_millis(self.poll_interval_ms)
+    }
+
+    /// Get the initial retry backoff as a `Duration`.
+    #[must_use]
+    pub const fn retry_backoff(&self) -> Duration {
+        Duration::from_millis(self.retry_backoff_ms)
+    }
+
+    /// Get the maximum retry backoff as a `Duration`.
+    #[must_use]
+    pub const fn max_retry_backoff(&self) -> Duration {
+        Duration::from_millis(self.max_retry_backoff_ms)
+    }
+
+    /// Get the retention of published events as a `Duration`.
+    #[must_use]
+    pub const fn retention(&self) -> Duration {
+        Duration::from_secs(self.retention_hours * 3600)
+    }
+}
+
+impl Default for OutboxSettings {
+    fn default() -> Self {
+        Self {
+            enabled: default_outbox_enabled(),
+            poll_interval_ms: default_outbox_poll_interval_ms(),
+            batch_size: default_outbox_batch_size(),
+            retry_backoff_ms: default_outbox_retry_backoff_ms(),
+            max_retry_backoff_ms: default_outbox_max_retry_backoff_ms(),
+            retention_hours: default_outbox_retention_hours(),
+        }
+    }
+}
+
+const fn default_outbox_enabled() -> bool {
+    true
+}
+
+const fn default_outbox_poll_interval_ms() -> u64 {
+    500
+}
+
+const fn default_outbox_batch_size() -> u32 {
+    500
+}
+
+const fn default_outbox_retry_backoff_ms() -> u64 {
+    1000
+}
+
+const fn default_outbox_max_retry_backoff_ms() -> u64 {
+    60_000
+}
+
+const fn default_outbox_retention_hours() -> u64 {
+    24
+}
+
+/// DeadPool round watcher configuration.
+///
+/// The round watcher scans the active rounds and publishes derived events to
+/// the `derived` topic: `RoundClosingSoon` as a round's deadline comes within
+/// each of `closing_soon_secs`, and `RoundAwaitingResolution` once it passes.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct RoundWatcherSettings {
+    /// Run the round watcher.
+    #[serde(default = "default_round_watcher_enabled")]
+    pub enabled: bool,
+    /// Interval between scans of the active rounds, in milliseconds.
+    #[serde(default = "default_round_watcher_poll_interval_ms")]
+    pub poll_interval_ms: u64,
+    /// Seconds before the deadline at which `RoundClosingSoon` is emitted.
+    #[serde(default = "default_round_watcher_closing_soon_secs")]
+    pub closing_soon_secs: Vec<u64>,
+    /// Most active rounds scanned per poll.
+    #[serde(default = "default_round_watcher_max_rounds")]
+    pub max_rounds: u32,
+}
+
+impl RoundWatcherSettings {
+    /// Get the poll interval as a `Duration`.
+    #[must_use]
+    pub const fn poll_interval(&self) -> Duration {
+        Duration::from_millis(self.poll_interval_ms)
+    }
+}
+
+impl Default for RoundWatcherSettings {
+    fn default() -> Self {
+        Self {
+            enabled: default_round_watcher_enabled(),
+            poll_interval_ms: default_round_watcher_poll_interval_ms(),
+            closing_soon_secs: default_round_watcher_closing_soon_secs(),
+            max_rounds: default_round_watcher_max_rounds(),
+        }
+    }
+}
+
+const fn default_round_watcher_enabled() -> bool {
+    true
+}
+
+const fn default_round_watcher_poll_interval_ms() -> u64 {
+    5000
+}
+
+fn default_round_watcher_closing_soon_secs() -> Vec<u64> {
+    vec![3600, 600, 60]
+}
+
+const fn default_round_watcher_max_rounds() -> u32 {
+    500
+}
+
+/// DATA holder balance configuration.
+///
+/// The token handler maintains each address's balance from `Transfer`
+/// events. The holder reconciler compares `reconcile_sample_size` of them,
+/// flagged ones first, with `balanceOf` every `reconcile_interval_secs` and
+/// corrects those that drifted.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct HolderSettings {
+    /// Maintain holder balances from transfers.
+    #[serde(default = "default_holders_enabled")]
+    pub enabled: bool,
+    /// Compare holder balances with the chain periodically.
+    #[serde(default = "default_holders_reconcile")]
+    pub reconcile: bool,
+    /// Interval between reconciliations, in seconds.
+    #[serde(default = "default_holders_reconcile_interval_secs")]
+    pub reconcile_interval_secs: u64,
+    /// Holders compared per reconciliation.
+    #[serde(default = "default_holders_reconcile_sample_size")]
+    pub reconcile_sample_size: u32,
+}
+
+impl HolderSettings {
+    /// Get the reconcile interval as a `Duration`.
+    #[must_use]
+    pub const fn reconcile_interval(&self) -> Duration {
+        Duration::from_secs(self.reconcile_interval_secs)
+    }
+}
+
+impl Default for HolderSettings {
+    fn default() -> Self {
+        Self {
+            enabled: default_holders_enabled(),
+            reconcile: default_holders_reconcile(),
+            reconcile_interval_secs: default_holders_reconcile_interval_secs(),
+            reconcile_sample_size: default_holders_reconcile_sample_size(),
+        }
+    }
+}
+
+const fn default_holders_enabled() -> bool {
+    true
+}
+
+const fn default_holders_reconcile() -> bool {
+    true
+}
+
+const fn default_holders_reconcile_interval_secs() -> u64 {
+    3600
+}
+
+const fn default_holders_reconcile_sample_size() -> u32 {
+    100
+}
+
+/// Boost expiry tracking configuration.
+///
+/// The position handler saves each applied boost. The boost expirer checks
+/// every `poll_interval_ms` for boosts that lapsed or whose position closed,
+/// records them as expired and publishes `BoostExpired` to the `derived`
+/// topic.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct BoostSettings {
+    /// Save applied boosts and run the boost expirer.
+    #[serde(default = "default_boosts_enabled")]
+    pub enabled: bool,
+    /// Interval between expiry checks, in milliseconds.
+    #[serde(default = "default_boosts_poll_interval_ms")]
+    pub poll_interval_ms: u64,
+    /// Most boosts expired per check.
+    #[serde(default = "default_boosts_max_boosts")]
+    pub max_boosts: u32,
+}
+
+impl BoostSettings {
+    /// Get the poll interval as a `Duration`.
+    #[must_use]
+    pub const fn poll_interval(&self) -> Duration {
+        Duration::from_millis(self.poll_interval_ms)
+    }
+}
+
+impl Default for BoostSettings {
+    fn default() -> Self {
+        Self {
+            enabled: default_boosts_enabled(),
+            poll_interval_ms: default_boosts_poll_interval_ms(),
+            max_boosts: default_boosts_max_boosts(),
+        }
+    }
+}
+
+const fn default_boosts_enabled() -> bool {
+    true
+}
+
+const fn default_boosts_poll_interval_ms() -> u64 {
+    5000
+}
+
+const fn default_boosts_max_boosts() -> u32 {
+    500
+}
+
+/// Poison event quarantine configuration.
+///
+/// A handler failing on a log with a transient store error is retried up
+/// to `max_retries` times. A log it still fails on, or fails on for good
+/// (e.g. a log that does not decode), is quarantined in the
+/// `quarantined_events` table and indexing carries on with the next log.
+/// Handlers listed in `strict_ordering` also quarantine the later logs of
+/// the same aggregate, e.g. a user's position, until the first is retried.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct QuarantineSettings {
+    /// Quarantine failing logs; without it, they are logged and skipped.
+    #[serde(default = "default_quarantine_enabled")]
+    pub enabled: bool,
+    /// Retries of a transient store error before the log is quarantined.
+    #[serde(default = "default_quarantine_max_retries")]
+    pub max_retries: u32,
+    /// Delay before the first retry, in milliseconds; doubled per retry.
+    #[serde(default = "default_quarantine_retry_delay_ms")]
+    pub retry_delay_ms: u64,
+    /// Handlers whose later logs of an aggregate wait for a quarantined one.
+    #[serde(default = "default_quarantine_strict_ordering")]
+    pub strict_ordering: Vec<String>,
+    /// Quarantined logs at which a warning is logged.
+    #[serde(default = "default_quarantine_warn_depth")]
+    pub warn_depth: u64,
+}
+
+impl QuarantineSettings {
+    /// Get the policy for retrying transient handler errors.
+    #[must_use]
+    pub const fn retry_policy(&self) -> RetryPolicy {
+        RetryPolicy::new(self.max_retries, Duration::from_millis(self.retry_delay_ms))
+    }
+}
+
+impl Default for QuarantineSettings {
+    fn default() -> Self {
+        Self {
+            enabled: default_quarantine_enabled(),
+            max_retries: default_quarantine_max_retries(),
+            retry_delay_ms: default_quarantine_retry_delay_ms(),
+            strict_ordering: default_quarantine_strict_ordering(),
+            warn_depth: default_quarantine_warn_depth(),
+        }
+    }
+}
+
+const fn default_quarantine_enabled() -> bool {
+    true
+}
+
+const fn default_quarantine_max_retries() -> u32 {
+    3
+}
+
+const fn default_quarantine_retry_delay_ms() -> u64 {
+    100
+}
+
+fn default_quarantine_strict_ordering() -> Vec<String> {
+    vec!["position".into()]
+}
+
+const fn default_quarantine_warn_depth() -> u64 {
+    100
+}
+
+/// Wire schema configuration.
+///
+/// Every published event is encoded once per schema version listed for its
+/// topic, so listing a topic's old and new version publishes both during a
+/// deprecation window, and listing only the new one ends it. Topics not
+/// listed publish every version the indexer has.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct SchemaSettings {
+    /// Schema versions published per topic, e.g. `positions = [1, 2]`.
+    #[serde(default)]
+    pub versions: HashMap<String, Vec<u32>>,
+}
+
+/// Graceful shutdown configuration.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct ShutdownSettings {
+    /// Time allowed for in-flight work to drain after a shutdown signal, in
+    /// milliseconds. Remaining work is aborted once it elapses.
+    #[serde(default = "default_shutdown_grace_period_ms")]
+    pub grace_period_ms: u64,
+}
+
+impl ShutdownSettings {
+    /// Get the grace period as a `Duration`.
+    #[must_use]
+    pub const fn grace_period(&self) -> Duration {
+        Duration::from_millis(self.grace_period_ms)
+    }
+}
+
+impl Default for ShutdownSettings {
+    fn default() -> Self {
+        Self {
+            grace_period_ms: default_shutdown_grace_period_ms(),
+        }
+    }
+}
+
+const fn default_shutdown_grace_period_ms() -> u64 {
+    30_000
+}
+
+const fn default_token_flows_window_secs() -> u64 {
+    86_400
+}
+
+/// Logging configuration.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct LoggingSettings {
+    /// Log level (trace, debug, info, warn, error).
+    pub level: String,
+    /// Log format (json, pretty).
+    pub format: String,
+    /// Optional file path for log output.
+    pub file_path: Option<String>,
+}
+
+/// Metrics configuration.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct MetricsSettings {
+    /// Whether metrics are enabled.
+    pub enabled: bool,
+    /// Host to bind metrics server to.
+    pub host: String,
+    /// Port for metrics server.
+    pub port: u16,
+}
+
+impl MetricsSettings {
+    /// Get the metrics socket address string.
+    #[must_use]
+    pub fn socket_addr(&self) -> String {
+        format!("{}:{}", self.host, self.port)
+    }
+}
+
+/// GHOSTNET smart contract addresses.
+///
+/// These addresses point to the deployed contracts on MegaETH.
+/// All addresses should be checksummed. Contracts are named by their field
+/// name (e.g. `dead_pool`) in `disabled` and `code_hashes`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct ContractAddresses {
+    /// GhostCore contract - main game logic.
+    pub ghost_core: String,
+    /// TraceScan contract - scan execution.
+    pub trace_scan: String,
+    /// DeadPool contract - prediction market.
+    pub dead_pool: String,
+    /// DataToken contract - $DATA ERC20.
+    pub data_token: String,
+    /// FeeRouter contract - fee collection.
+    pub fee_router: String,
+    /// RewardsDistributor contract - emissions.
+    pub rewards_distributor: String,
+    /// Contracts not to index (e.g. `["dead_pool"]`).
+    #[serde(default)]
+    pub disabled: Vec<String>,
+    /// Expected hex prefixes of the keccak256 hash of each contract's
+    /// deployed bytecode, checked at startup.
+    #[serde(default)]
+    pub code_hashes: HashMap<String, String>,
+    /// Further deployments of a contract to index alongside its main
+    /// address, e.g. a previous `dead_pool` that still settles rounds.
+    #[serde(default)]
+    pub additional: HashMap<String, Vec<String>>,
+}
+
+impl ContractAddresses {
+    /// Get all contract addresses as a vector.
+    ///
+    /// Useful for building log filters covering all contracts.
+    #[must_use]
+    pub fn all(&self) -> Vec<&str> {
+        vec![
+            &self.ghost_core,
+            &self.trace_scan,
+            &self.dead_pool,
+            &self.data_token,
+            &self.fee_router,
+            &self.rewards_distributor,
+        ]
+    }
 
     /// Parse all addresses into Alloy Address types.
     ///
@@ -478,9 +1810,9 @@ mod tests {
             batch_size: 100,
         };
 
-        assert_eq!(rpc.poll_interval(), Duration::from_millis(1000));
+        assert_eq!(rpc.poll_interval(), Duration::from_secs(1));
         assert_eq!(rpc.retry_delay(), Duration::from_millis(500));
-        assert_eq!(rpc.request_timeout(), Duration::from_millis(30000));
+        assert_eq!(rpc.request_timeout(), Duration::from_secs(30));
     }
 
     #[test]
@@ -490,14 +1822,20 @@ mod tests {
             port: 8080,
             cors_origins: vec![],
             request_timeout_ms: 30000,
+            chain_read_timeout_ms: 2000,
             websocket: WebSocketSettings {
                 max_connections: 1000,
                 ping_interval_ms: 30000,
                 pong_timeout_ms: 10000,
+                replay_retention_secs: 3600,
+                replay_max_events: 10_000,
             },
             rate_limit: RateLimitSettings {
-                requests_per_second: 100,
-                burst_size: 200,
+                requests_per_minute: 600,
+                burst_size: 50,
+                websocket_connects_per_minute: 1200,
+                subscribe_messages_per_minute: 120,
+                trusted_proxy_header: None,
             },
         };
 
@@ -527,6 +1865,185 @@ mod tests {
         assert!(errors.iter().any(|e| e.contains("min_connections")));
     }
 
+    #[test]
+    fn validation_catches_truncated_application_name() {
+        let mut settings = create_valid_settings();
+        settings.database.application_name = "x".repeat(64);
+
+        let errors = settings.validate().unwrap_err();
+        assert!(errors.iter().any(|e| e.contains("application_name")));
+        assert_eq!(settings.database.retry_policy().max_retries, 3);
+    }
+
+    #[test]
+    fn validation_catches_leaderboard_limit_above_max() {
+        let mut settings = create_valid_settings();
+        settings.leaderboard.default_limit = 1000;
+        settings.leaderboard.max_entries = 500;
+
+        let result = settings.validate();
+        assert!(result.is_err());
+        let errors = result.unwrap_err();
+        assert!(errors.iter().any(|e| e.contains("leaderboard.default_limit")));
+    }
+
+    #[test]
+    fn validation_checks_outbox_only_when_enabled() {
+        let mut settings = create_valid_settings();
+        settings.outbox.batch_size = 0;
+        settings.outbox.retry_backoff_ms = 120_000;
+
+        let errors = settings.validate().unwrap_err();
+        assert_eq!(errors.len(), 2, "{errors:?}");
+        assert!(errors.iter().any(|e| e.contains("outbox.batch_size")));
+
+        settings.outbox.enabled = false;
+        assert!(settings.validate().is_ok());
+    }
+
+    #[test]
+    fn validation_catches_zero_closing_threshold() {
+        let mut settings = create_valid_settings();
+        settings.round_watcher.closing_soon_secs = vec![600, 0];
+
+        let errors = settings.validate().unwrap_err();
+        assert!(errors.iter().any(|e| e.contains("round_watcher.closing_soon_secs")));
+    }
+
+    #[test]
+    fn validation_catches_zero_reconcile_sample() {
+        let mut settings = create_valid_settings();
+        settings.holders.reconcile_sample_size = 0;
+        assert!(settings.validate().is_err());
+
+        // Only checked when holders are reconciled
+        settings.holders.reconcile = false;
+        assert!(settings.validate().is_ok());
+    }
+
+    #[test]
+    fn validation_catches_zero_boost_poll_interval() {
+        let mut settings = create_valid_settings();
+        settings.boosts.poll_interval_ms = 0;
+        let errors = settings.validate().unwrap_err();
+        assert!(errors.iter().any(|e| e.contains("boosts.poll_interval_ms")));
+
+        // Only checked when boosts are tracked
+        settings.boosts.enabled = false;
+        assert!(settings.validate().is_ok());
+    }
+
+    #[test]
+    fn validation_catches_unknown_strict_ordering_handlers() {
+        let mut settings = create_valid_settings();
+        settings.quarantine.strict_ordering = vec!["position".into(), "positions".into()];
+        let errors = settings.validate().unwrap_err();
+        assert_eq!(
+            errors,
+            vec!["quarantine.strict_ordering: unknown handler 'positions'"]
+        );
+    }
+
+    #[test]
+    fn validation_catches_unsupported_schema_versions() {
+        let mut settings = create_valid_settings();
+        settings.schemas.versions = HashMap::from([
+            ("positions".to_string(), vec![1, 2]),
+            ("market".to_string(), vec![1, 2]),
+            ("trades".to_string(), vec![1]),
+            ("scans".to_string(), vec![]),
+        ]);
+
+        let mut errors = settings.validate().unwrap_err();
+        errors.sort();
+        assert_eq!(
+            errors,
+            [
+                "schemas.versions.market: unsupported schema version 2",
+                "schemas.versions.scans cannot be empty",
+                "schemas.versions: unknown topic 'trades'",
+            ]
+        );
+    }
+
+    #[test]
+    fn validation_catches_unknown_contract_name() {
+        let mut settings = create_valid_settings();
+        settings.contracts.disabled = vec!["dead_pool".into(), "dead_pol".into()];
+
+        let errors = settings.validate().unwrap_err();
+        assert_eq!(errors, vec!["contracts: unknown contract 'dead_pol'".to_string()]);
+    }
+
+    #[test]
+    fn single_chain_is_rpc_and_contracts() {
+        let settings = create_valid_settings();
+        let chains = settings.indexed_chains();
+        assert_eq!(chains.len(), 1);
+        assert_eq!(chains[0].chain_id, 1);
+        assert_eq!(chains[0].rpc, settings.rpc);
+        assert_eq!(chains[0].schema, "public");
+        assert_eq!(chains[0].stream_name, "ghostnet");
+        assert_eq!(settings.primary_chain_id(), Some(1));
+    }
+
+    #[test]
+    fn chains_get_their_own_schema_and_stream() {
+        let mut settings = create_valid_settings();
+        let mut testnet = chain(6343);
+        testnet.schema = Some("public".into());
+        let mut disabled = chain(6342);
+        disabled.enabled = false;
+        settings.chains = vec![testnet, chain(4326), disabled];
+        settings.primary_chain = Some(4326);
+        assert!(settings.validate().is_ok());
+
+        let chains = settings.indexed_chains();
+        let ids: Vec<_> = chains.iter().map(|chain| chain.chain_id).collect();
+        assert_eq!(ids, [6343, 4326]);
+        assert_eq!(chains[1].schema, "chain_4326");
+        assert_eq!(chains[1].stream_name, "ghostnet-4326");
+        assert_eq!(chains[1].rpc.url, "https://4326.rpc.example");
+        assert_eq!(chains[1].rpc.batch_size, settings.rpc.batch_size);
+
+        let mainnet = settings.for_chain(&chains[1]);
+        assert_eq!(mainnet.rpc.chain_id, 4326);
+        assert_eq!(mainnet.iggy.stream_name, "ghostnet-4326");
+        assert_eq!(mainnet.database, settings.database);
+
+        let primary = settings.indexed_chain(None);
+        assert_eq!(primary.map(|chain| chain.chain_id), Some(4326));
+        let testnet = settings.indexed_chain(Some(6343));
+        assert_eq!(testnet.map(|chain| chain.schema).as_deref(), Some("public"));
+        assert_eq!(settings.indexed_chain(Some(6342)), None);
+    }
+
+    #[test]
+    fn validation_catches_chains_sharing_data() {
+        let mut settings = create_valid_settings();
+        let mut mainnet = chain(4326);
+        mainnet.schema = Some("chain_6343".into());
+        let mut invalid = chain(7);
+        invalid.schema = Some("Chain-7".into());
+        settings.chains = vec![chain(6343), mainnet, chain(6343), invalid];
+        settings.primary_chain = Some(1);
+
+        let mut errors = settings.validate().unwrap_err();
+        errors.sort();
+        assert_eq!(
+            errors,
+            [
+                "chains.7.schema must be a lowercase identifier of at most 63 bytes",
+                "chains: chain 6343 is listed more than once",
+                "chains: schema 'chain_6343' is used twice",
+                "chains: schema 'chain_6343' is used twice",
+                "chains: stream 'ghostnet-6343' is used twice",
+                "primary_chain 1 is not an indexed chain",
+            ]
+        );
+    }
+
+    #[allow(clippy::too_many_lines)] // One literal of every section
     fn create_valid_settings() -> Settings {
         Settings {
             rpc: RpcSettings {
@@ -545,6 +2062,12 @@ mod tests {
                 min_connections: 1,
                 connect_timeout_ms: 5000,
                 idle_timeout_ms: 600_000,
+                batch_writes: true,
+                batch_window_ms: 0,
+                statement_timeout_ms: 30_000,
+                application_name: "ghostnet-indexer".into(),
+                max_retries: 3,
+                retry_delay_ms: 100,
             },
             iggy: IggySettings {
                 url: "tcp://localhost:8090".into(),
@@ -553,20 +2076,34 @@ mod tests {
                 replication_factor: 1,
                 username: "iggy".into(),
                 password: "iggy".into(),
+                max_batch_size: 500,
+                max_batch_delay_ms: 50,
+                max_retries: 5,
+                retry_backoff_ms: 100,
+                dead_letter_path: None,
+                dead_letter_topic: "dead-letter".into(),
+                health_max_buffered: 10_000,
+                health_max_dead_lettered: 100,
             },
             api: ApiSettings {
                 host: "0.0.0.0".into(),
                 port: 8080,
                 cors_origins: vec![],
                 request_timeout_ms: 30000,
+                chain_read_timeout_ms: 2000,
                 websocket: WebSocketSettings {
                     max_connections: 10000,
                     ping_interval_ms: 30000,
                     pong_timeout_ms: 10000,
+                    replay_retention_secs: 3600,
+                    replay_max_events: 10_000,
                 },
                 rate_limit: RateLimitSettings {
-                    requests_per_second: 100,
-                    burst_size: 200,
+                    requests_per_minute: 600,
+                    burst_size: 50,
+                    websocket_connects_per_minute: 1200,
+                    subscribe_messages_per_minute: 120,
+                    trusted_proxy_header: None,
                 },
             },
             cache: CacheSettings {
@@ -576,6 +2113,20 @@ mod tests {
                 leaderboard_max_capacity: 1000,
                 stats_ttl_ms: 10000,
             },
+            stats: StatsSettings::default(),
+            leaderboard: LeaderboardSettings::default(),
+            token_flows: TokenFlowSettings::default(),
+            tx_context: TxContextSettings::default(),
+            raw_logs: RawLogSettings::default(),
+            scan_prediction: ScanPredictionSettings::default(),
+            user_profiles: UserProfileSettings::default(),
+            outbox: OutboxSettings::default(),
+            round_watcher: RoundWatcherSettings::default(),
+            holders: HolderSettings::default(),
+            boosts: BoostSettings::default(),
+            quarantine: QuarantineSettings::default(),
+            schemas: SchemaSettings::default(),
+            shutdown: ShutdownSettings::default(),
             logging: LoggingSettings {
                 level: "info".into(),
                 format: "json".into(),
@@ -593,7 +2144,24 @@ mod tests {
                 data_token: "0x0000000000000000000000000000000000000004".into(),
                 fee_router: "0x0000000000000000000000000000000000000005".into(),
                 rewards_distributor: "0x0000000000000000000000000000000000000006".into(),
+                disabled: vec![],
+                code_hashes: HashMap::new(),
+                additional: HashMap::new(),
             },
+            chains: vec![],
+            primary_chain: None,
+        }
+    }
+
+    fn chain(chain_id: u64) -> ChainSettings {
+        ChainSettings {
+            chain_id,
+            enabled: true,
+            url: format!("https://{chain_id}.rpc.example"),
+            ws_url: String::new(),
+            schema: None,
+            stream_name: None,
+            contracts: create_valid_settings().contracts,
         }
     }
 }
@@ -178,6 +178,11 @@ pub enum AppError {
     #[error("chain reorg detected at block {0}")]
     ReorgDetected(u64),
 
+    /// Event quarantined after its handler failed on it, with the ID of the
+    /// quarantined event.
+    #[error("event quarantined as #{0}")]
+    Quarantined(i64),
+
     /// Configuration error.
     #[error("configuration error: {0}")]
     Config(String),
@@ -219,10 +224,21 @@ pub enum ApiError {
     #[error("invalid request: {0}")]
     BadRequest(String),
 
+    /// Requested resource does not exist.
+    #[error("not found: {0}")]
+    NotFound(String),
+
     /// Authentication required or failed.
     #[error("unauthorized")]
     Unauthorized,
 
+    /// An upstream service (e.g. the RPC node) failed.
+    #[error("upstream failed: {class}")]
+    BadGateway {
+        /// Kind of failure, e.g. `timeout` or `reverted`.
+        class: &'static str,
+    },
+
     /// Internal server error (with source for logging).
     #[error("internal error")]
     Internal(#[source] eyre::Report),
@@ -236,7 +252,8 @@ impl IntoResponse for ApiError {
                 DomainError::PositionNotFound(_)
                 | DomainError::ScanNotFound { .. }
                 | DomainError::RoundNotFound(_),
-            )) => (StatusCode::NOT_FOUND, "NOT_FOUND", self.to_string()),
+            ))
+            | Self::NotFound(_) => (StatusCode::NOT_FOUND, "NOT_FOUND", self.to_string()),
 
             Self::App(AppError::Domain(
                 DomainError::InvalidLevel(_)
@@ -280,10 +297,26 @@ impl IntoResponse for ApiError {
 
             Self::Unauthorized => (StatusCode::UNAUTHORIZED, "UNAUTHORIZED", self.to_string()),
 
+            Self::BadGateway { class } => {
+                tracing::warn!(class, "Upstream error");
+                return (
+                    StatusCode::BAD_GATEWAY,
+                    Json(json!({
+                        "error": {
+                            "code": "BAD_GATEWAY",
+                            "message": self.to_string(),
+                            "upstream": class
+                        }
+                    })),
+                )
+                    .into_response();
+            }
+
             // Infrastructure and internal errors: log but don't expose details
             Self::App(
                 AppError::Infra(_)
                 | AppError::ReorgDetected(_)
+                | AppError::Quarantined(_)
                 | AppError::Config(_)
                 | AppError::Initialization(_)
                 | AppError::ShutdownRequested,
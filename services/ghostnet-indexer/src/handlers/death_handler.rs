@@ -3,7 +3,8 @@
 //! Handles all death-related events from the `GhostCore` contract:
 //! - `DeathsProcessed` - Deaths are marked after a scan
 //! - `SurvivorsUpdated` - Ghost streaks incremented for survivors
-//! - `CascadeDistributed` - Rewards distributed to survivors and upstream levels
+//! - `CascadeDistributed` - Rewards distributed to survivors and upstream levels,
+//!   attributed to the receiving positions pro rata to stake
 //! - `EmissionsAdded` - Emissions added to a level
 //! - `SystemResetTriggered` - Doomsday clock reset
 //!
@@ -26,18 +27,24 @@
 //! - Uses `DeathStore` port for death records
 //! - Uses `PositionStore` port for position updates
 //! - Uses `Cache` port for cache invalidation
+//! - Uses `StatsSink` port (optional) for aggregate level and global statistics
+//! - Uses [`ScanCorrelator`] (optional) to link scan deaths to their scan
 
 use std::sync::Arc;
 
+use alloy::primitives::{U256, U512};
 use async_trait::async_trait;
 use tracing::{debug, info, instrument, warn};
 use uuid::Uuid;
 
 use crate::abi::ghost_core;
 use crate::error::Result;
-use crate::handlers::DeathPort;
-use crate::ports::{Cache, DeathStore, PositionStore};
-use crate::types::entities::{Death, PositionAction, PositionHistoryEntry};
+use crate::handlers::{DeathPort, ScanCorrelator};
+use crate::ports::{Cache, DeathStore, PositionStore, StatsSink};
+use crate::types::entities::{
+    CascadeShare, Death, GlobalStatsDelta, LevelStatsDelta, Position, PositionAction,
+    PositionHistoryEntry,
+};
 use crate::types::enums::{ExitReason, Level};
 use crate::types::events::EventMetadata;
 use crate::types::primitives::{BlockNumber, EthAddress, TokenAmount};
@@ -65,6 +72,10 @@ pub struct DeathHandler<D, P, C> {
     position_store: Arc<P>,
     /// Cache for invalidation.
     cache: Arc<C>,
+    /// Sink for aggregate statistics.
+    stats: Option<Arc<dyn StatsSink>>,
+    /// Correlator shared with the scan handler.
+    correlator: Option<Arc<ScanCorrelator>>,
 }
 
 impl<D, P, C> DeathHandler<D, P, C>
@@ -79,6 +90,112 @@ where
             death_store,
             position_store,
             cache,
+            stats: None,
+            correlator: None,
+        }
+    }
+
+    /// Record aggregate statistics through the given sink.
+    ///
+    /// The sink then owns level cache invalidation for the levels it is
+    /// handed deltas for.
+    #[must_use]
+    pub fn with_stats(mut self, stats: Arc<dyn StatsSink>) -> Self {
+        self.stats = Some(stats);
+        self
+    }
+
+    /// Link recorded scan deaths to their scan through the given correlator.
+    ///
+    /// The same correlator must be given to the `ScanHandler`.
+    #[must_use]
+    pub fn with_correlator(mut self, correlator: Arc<ScanCorrelator>) -> Self {
+        self.correlator = Some(correlator);
+        self
+    }
+
+    /// Record the victims of a `submitDeaths` batch.
+    ///
+    /// `DeathsProcessed` does not carry victim addresses, so this is the
+    /// entry point for a victim source (e.g. decoded `submitDeaths`
+    /// calldata) sharing the event's metadata. Each victim's active position
+    /// on `level` is closed as traced and a death record is stored. The
+    /// records carry the scan ID when the batch's `DeathsSubmitted` event
+    /// has been seen; otherwise the scan handler links them once it is.
+    ///
+    /// Victims without an active position on `level` are skipped. Returns
+    /// the IDs of the recorded deaths.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a store operation fails.
+    #[instrument(skip(self, victims, meta), fields(level = ?level, victims = victims.len()))]
+    pub async fn record_scan_deaths(
+        &self,
+        level: Level,
+        victims: &[EthAddress],
+        meta: &EventMetadata,
+    ) -> Result<Vec<Uuid>> {
+        let mut deaths = Vec::with_capacity(victims.len());
+        let mut level_delta = LevelStatsDelta::default();
+
+        for victim in victims {
+            let Some(mut position) = self.position_store.get_active_position(victim).await? else {
+                warn!(victim = %victim, "Traced victim has no active position, skipping");
+                continue;
+            };
+            if position.level != level {
+                warn!(victim = %victim, position_level = ?position.level, "Traced victim is on another level, skipping");
+                continue;
+            }
+
+            self.close(&mut position, ExitReason::Traced, meta).await?;
+            let entry = Self::history_entry(
+                &position,
+                PositionAction::Traced,
+                position.amount.clone(),
+                meta,
+            );
+            self.position_store
+                .save_position_with_history(&position, &entry, &[])
+                .await?;
+
+            deaths.push(Death {
+                id: Uuid::new_v4(),
+                scan_id: None,
+                user_address: position.user_address,
+                position_id: Some(position.id),
+                amount_lost: position.amount.clone(),
+                level,
+                ghost_streak_at_death: Some(position.ghost_streak),
+                created_at: meta.timestamp,
+            });
+            // Death counts come from ScanFinalized
+            level_delta.merge(LevelStatsDelta::closed(&position));
+        }
+
+        let ids: Vec<_> = deaths.iter().map(|d| d.id).collect();
+        if let Some(correlator) = &self.correlator
+            && let Some(scan_id) = correlator.attach(meta.tx_hash, level, &ids)
+        {
+            for death in &mut deaths {
+                death.scan_id = Some(scan_id);
+            }
+        }
+
+        self.death_store.record_deaths(&deaths).await?;
+        self.record_stats(level, level_delta, meta);
+
+        debug!(recorded = ids.len(), "Scan deaths recorded");
+        Ok(ids)
+    }
+
+    /// Record a level stats delta, or invalidate the level directly when no
+    /// stats sink is configured.
+    fn record_stats(&self, level: Level, delta: LevelStatsDelta, meta: &EventMetadata) {
+        match &self.stats {
+            Some(stats) => stats.record_level(level, delta, meta.timestamp),
+            None => self.cache.invalidate_level(&level),
         }
     }
 
@@ -97,29 +214,143 @@ where
         EthAddress::new(addr.0.0)
     }
 
-    /// Record a position history entry.
-    async fn record_history(
+    /// Split a cascade between the positions alive on the receiving levels.
+    ///
+    /// Same-level rewards go to survivors on `source_level`. Upstream rewards
+    /// are split between levels by TVL and within each level by stake, which
+    /// is a split by stake across all upstream positions.
+    ///
+    /// Stakes are the raw `amount`, not the effective stake: `GhostCore`
+    /// pays cascades by stake whatever yield multipliers are active.
+    async fn cascade_shares(
+        &self,
+        source_level: Level,
+        same_level: U256,
+        upstream: U256,
+        meta: &EventMetadata,
+    ) -> Result<Vec<CascadeShare>> {
+        let upstream_levels: Vec<_> = (Level::Vault as u8..source_level as u8)
+            .filter_map(|level| Level::try_from(level).ok())
+            .collect();
+
+        let mut shares = Vec::new();
+        for (amount, levels) in [
+            (same_level, std::slice::from_ref(&source_level)),
+            (upstream, upstream_levels.as_slice()),
+        ] {
+            if amount.is_zero() {
+                continue;
+            }
+            let survivors = self.survivors(levels).await?;
+            if survivors.is_empty() {
+                warn!(
+                    source_level = ?source_level,
+                    levels = ?levels,
+                    "No positions to attribute cascade to"
+                );
+                continue;
+            }
+            let stakes: Vec<_> = survivors
+                .iter()
+                .map(|p| p.amount.to_wei(DATA_TOKEN_DECIMALS))
+                .collect();
+            shares.extend(
+                split_pro_rata(amount, &stakes)
+                    .into_iter()
+                    .zip(&survivors)
+                    .filter(|(share, _)| !share.is_zero())
+                    .map(|(share, position)| CascadeShare {
+                        id: Uuid::new_v4(),
+                        position_id: position.id,
+                        user_address: position.user_address,
+                        source_level,
+                        level: position.level,
+                        amount: Self::to_token_amount(&share),
+                        block_number: BlockNumber::new(meta.block_number),
+                        created_at: meta.timestamp,
+                    }),
+            );
+        }
+        Ok(shares)
+    }
+
+    /// Active positions on `levels`, ordered by address so that rounding dust
+    /// is assigned the same way however the store orders them.
+    async fn survivors(&self, levels: &[Level]) -> Result<Vec<Position>> {
+        let mut survivors = Vec::new();
+        for &level in levels {
+            let positions = self.position_store.get_positions_by_level(level).await?;
+            survivors.extend(positions.into_iter().filter(Position::is_active));
+        }
+        survivors.sort_by_key(|p| *p.user_address.as_bytes());
+        Ok(survivors)
+    }
+
+    /// Close `position` for `reason`, counting the scans it survived.
+    async fn close(
         &self,
-        position_id: Uuid,
-        user_address: EthAddress,
+        position: &mut Position,
+        reason: ExitReason,
+        meta: &EventMetadata,
+    ) -> Result<()> {
+        let scans_survived = self
+            .position_store
+            .count_scans_survived(
+                position.level,
+                position.created_at_block,
+                BlockNumber::new(meta.block_number),
+            )
+            .await?;
+        position.close(reason, meta.timestamp, scans_survived);
+        Ok(())
+    }
+
+    /// History entry for a position closed by `action`; the whole stake is lost.
+    fn history_entry(
+        position: &Position,
         action: PositionAction,
         amount_change: TokenAmount,
-        new_total: TokenAmount,
         meta: &EventMetadata,
-    ) -> Result<()> {
-        let entry = PositionHistoryEntry {
-            id: Uuid::new_v4(),
-            position_id,
-            user_address,
+    ) -> PositionHistoryEntry {
+        PositionHistoryEntry::new(
+            position,
             action,
             amount_change,
-            new_total,
-            block_number: BlockNumber::new(meta.block_number),
-            timestamp: meta.timestamp,
-        };
+            BlockNumber::new(meta.block_number),
+            meta.timestamp,
+        )
+    }
+}
+
+/// Split `amount` between `weights` pro rata, rounding down.
+///
+/// The rounding dust (less than one unit per weight) is handed out one unit
+/// at a time to the largest remainders, ties going to the earlier weight, so
+/// the shares sum to exactly `amount`. All shares are zero if the weights are.
+fn split_pro_rata(amount: U256, weights: &[U256]) -> Vec<U256> {
+    let total = weights
+        .iter()
+        .fold(U512::ZERO, |total, weight| total + U512::from(*weight));
+    if total.is_zero() {
+        return vec![U256::ZERO; weights.len()];
+    }
 
-        self.position_store.record_history(&entry).await
+    let (mut shares, remainders): (Vec<U256>, Vec<U512>) = weights
+        .iter()
+        .map(|weight| {
+            let (share, remainder) = amount.widening_mul::<256, 4, 512, 8>(*weight).div_rem(total);
+            (share.saturating_to::<U256>(), remainder)
+        })
+        .unzip();
+
+    let distributed = shares.iter().fold(U256::ZERO, |sum, share| sum + share);
+    let dust: usize = amount.saturating_sub(distributed).saturating_to();
+    let mut order: Vec<usize> = (0..weights.len()).collect();
+    order.sort_by(|&a, &b| remainders[b].cmp(&remainders[a]));
+    for i in order.into_iter().take(dust) {
+        shares[i] += U256::from(1);
     }
+    shares
 }
 
 #[async_trait]
@@ -135,8 +366,8 @@ where
     /// We record death records and update affected positions.
     ///
     /// Note: Individual victim addresses are not included in this event.
-    /// Those come from the `DeathsSubmitted` events in `TraceScan` or
-    /// would need to be fetched from on-chain state.
+    /// They are recorded through [`DeathHandler::record_scan_deaths`] and
+    /// linked to their scan through the [`ScanCorrelator`].
     #[instrument(skip(self, event, meta), fields(level = event.level, count = %event.count))]
     async fn handle_deaths_processed(
         &self,
@@ -150,13 +381,7 @@ where
         let distributed = Self::to_token_amount(&event.distributed);
 
         // Note: This event doesn't include individual victim addresses.
-        // In a full implementation, we would:
-        // 1. Track a pending scan ID
-        // 2. Correlate with DeathsSubmitted events to get victim lists
-        // 3. Or fetch victims from on-chain logs/state
-        //
-        // For now, we log the aggregate data. Individual death records
-        // would be created when we implement the full death tracking flow.
+        // Individual death records are created by `record_scan_deaths`.
 
         debug!(
             level = ?level,
@@ -223,6 +448,9 @@ where
     /// - 30% to upstream (safer) levels
     /// - 30% burned
     /// - 10% to protocol treasury
+    ///
+    /// The event only carries totals, so each receiving position's share is
+    /// derived from the positions alive at the time and recorded.
     #[instrument(skip(self, event, meta), fields(source_level = event.sourceLevel))]
     async fn handle_cascade_distributed(
         &self,
@@ -246,9 +474,20 @@ where
             "Cascade distributed"
         );
 
-        // Invalidate caches for affected levels
-        // Same level and all upstream (safer) levels
-        self.cache.invalidate_level(&source_level);
+        let shares = self
+            .cascade_shares(source_level, event.sameLevelAmount, event.upstreamAmount, &meta)
+            .await?;
+        self.death_store.record_cascade_shares(&shares).await?;
+
+        // Burned and distributed totals are attributed to the level the deaths occurred on
+        self.record_stats(
+            source_level,
+            LevelStatsDelta::cascade(
+                burn_amount.clone(),
+                same_level_amount.saturating_add(&upstream_amount),
+            ),
+            &meta,
+        );
 
         // Invalidate upstream levels (Vault receives from all, etc.)
         for level_value in 0..source_level as u8 {
@@ -337,6 +576,7 @@ where
             if let Ok(level) = Level::try_from(level_value) {
                 // Get all active positions for this level
                 let positions = self.position_store.get_positions_by_level(level).await?;
+                let mut level_delta = LevelStatsDelta::default();
 
                 for mut position in positions {
                     if !position.is_active() {
@@ -344,24 +584,19 @@ where
                     }
 
                     // Close the position
-                    position.is_alive = false;
-                    position.exit_reason = Some(ExitReason::SystemReset);
-                    position.exit_timestamp = Some(meta.timestamp);
-                    position.updated_at = meta.timestamp;
-
-                    // Save updated position
-                    self.position_store.save_position(&position).await?;
-
-                    // Record history
-                    self.record_history(
-                        position.id,
-                        position.user_address,
+                    self.close(&mut position, ExitReason::SystemReset, &meta)
+                        .await?;
+
+                    // Save updated position with history (amount lost, new total zero)
+                    let entry = Self::history_entry(
+                        &position,
                         PositionAction::SystemReset,
-                        position.amount.clone(), // Amount lost
-                        TokenAmount::zero(),     // New total is zero
+                        position.amount.clone(),
                         &meta,
-                    )
-                    .await?;
+                    );
+                    self.position_store
+                        .save_position_with_history(&position, &entry, &[])
+                        .await?;
 
                     // Create death record
                     let death = Death {
@@ -376,13 +611,20 @@ where
                     };
 
                     self.death_store.record_deaths(&[death]).await?;
+                    level_delta.merge(LevelStatsDelta::died(&position));
                 }
 
-                // Invalidate cache for this level
-                self.cache.invalidate_level(&level);
+                self.record_stats(level, level_delta, &meta);
             }
         }
 
+        if let Some(stats) = &self.stats {
+            stats.record_global(GlobalStatsDelta {
+                system_resets_delta: Some(1),
+                ..GlobalStatsDelta::default()
+            });
+        }
+
         info!(
             total_penalty = %total_penalty,
             jackpot_winner = %jackpot_winner,
@@ -399,7 +641,7 @@ where
 // ═══════════════════════════════════════════════════════════════════════════════
 
 #[cfg(test)]
-#[allow(clippy::unwrap_used)]
+#[allow(clippy::unwrap_used, clippy::significant_drop_tightening)]
 mod tests {
     use std::collections::HashMap;
     use std::sync::RwLock;
@@ -410,7 +652,10 @@ mod tests {
 
     use super::*;
     use crate::ports::MockCache;
-    use crate::types::entities::{Position, PositionHistoryEntry};
+    use crate::types::entities::{
+        CascadeEarnings, HistoryCursor, OutboxEvent, Page, Position, PositionFilter,
+        PositionHistoryEntry,
+    };
     use crate::types::enums::Level;
     use crate::types::primitives::GhostStreak;
 
@@ -422,6 +667,7 @@ mod tests {
     #[derive(Debug, Default)]
     struct MockDeathStore {
         deaths: RwLock<Vec<Death>>,
+        cascade_shares: RwLock<Vec<CascadeShare>>,
     }
 
     impl MockDeathStore {
@@ -432,6 +678,10 @@ mod tests {
         fn death_count(&self) -> usize {
             self.deaths.read().unwrap().len()
         }
+
+        fn cascade_shares(&self) -> Vec<CascadeShare> {
+            self.cascade_shares.read().unwrap().clone()
+        }
     }
 
     #[async_trait]
@@ -461,6 +711,23 @@ mod tests {
             result.truncate(limit as usize);
             Ok(result)
         }
+
+        async fn record_cascade_shares(&self, shares: &[CascadeShare]) -> Result<()> {
+            let mut store = self.cascade_shares.write().unwrap();
+            store.extend(shares.iter().cloned());
+            Ok(())
+        }
+
+        async fn get_cascade_earnings(
+            &self,
+            _address: &EthAddress,
+            _limit: u32,
+        ) -> Result<CascadeEarnings> {
+            Ok(CascadeEarnings {
+                total: TokenAmount::zero(),
+                scans: vec![],
+            })
+        }
     }
 
     /// Mock position store for testing.
@@ -534,12 +801,31 @@ mod tests {
             Ok(vec![])
         }
 
-        async fn record_history(&self, entry: &PositionHistoryEntry) -> Result<()> {
+        async fn append_history(&self, entry: &PositionHistoryEntry) -> Result<()> {
             let mut history = self.history.write().unwrap();
             history.push(entry.clone());
             Ok(())
         }
 
+        async fn save_position_with_history(
+            &self,
+            position: &Position,
+            entry: &PositionHistoryEntry,
+            _: &[OutboxEvent],
+        ) -> Result<()> {
+            self.save_position(position).await?;
+            self.append_history(entry).await
+        }
+
+        async fn get_history(
+            &self,
+            _address: &EthAddress,
+            _limit: u32,
+            _before: Option<HistoryCursor>,
+        ) -> Result<Vec<PositionHistoryEntry>> {
+            Ok(vec![])
+        }
+
         async fn get_position_by_id(&self, _id: &Uuid) -> Result<Option<Position>> {
             Ok(None)
         }
@@ -553,6 +839,14 @@ mod tests {
                 .collect())
         }
 
+        async fn query(&self, _: &PositionFilter, _: u32) -> Result<Page<Position>> {
+            Ok(Page {
+                items: vec![],
+                next_cursor: None,
+            })
+        }
+
+        #[allow(clippy::cast_possible_truncation)]
         async fn count_positions_by_level(&self, level: Level) -> Result<u32> {
             let positions = self.positions.read().unwrap();
             Ok(positions
@@ -560,6 +854,15 @@ mod tests {
                 .filter(|p| p.level == level && p.is_alive)
                 .count() as u32)
         }
+
+        async fn count_scans_survived(
+            &self,
+            _level: Level,
+            _entered: BlockNumber,
+            _exited: BlockNumber,
+        ) -> Result<u32> {
+            Ok(0)
+        }
     }
 
     // ═══════════════════════════════════════════════════════════════════════════
@@ -585,6 +888,9 @@ mod tests {
             log_index: 0,
             timestamp: Utc::now(),
             contract: test_address(),
+            chain_id: 6343,
+            tx_function: None,
+            tx_from: None,
         }
     }
 
@@ -598,6 +904,7 @@ mod tests {
             user_address,
             level,
             amount: TokenAmount::parse("1000").unwrap(),
+            effective_stake: TokenAmount::parse("1000").unwrap(),
             reward_debt: TokenAmount::zero(),
             entry_timestamp: Utc::now(),
             last_add_timestamp: None,
@@ -606,6 +913,8 @@ mod tests {
             is_extracted: false,
             exit_reason: None,
             exit_timestamp: None,
+            survival_seconds: None,
+            scans_survived: None,
             extracted_amount: None,
             extracted_rewards: None,
             created_at_block: BlockNumber::new(900),
@@ -619,6 +928,7 @@ mod tests {
         EthAddress::new(bytes)
     }
 
+    #[allow(clippy::type_complexity)]
     fn create_handler() -> (
         DeathHandler<MockDeathStore, MockPositionStore, MockCache>,
         Arc<MockDeathStore>,
@@ -636,6 +946,7 @@ mod tests {
         (handler, death_store, position_store, cache)
     }
 
+    #[allow(clippy::type_complexity)]
     fn create_handler_with_position(
         position: Position,
     ) -> (
@@ -716,6 +1027,110 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn cascade_is_attributed_pro_rata_to_stake() {
+        let stake = |byte: u8, level: Level, amount: &str| Position {
+            amount: TokenAmount::parse(amount).unwrap(),
+            ..create_test_position_for_user(level, eth_address_from_byte(byte))
+        };
+        let dead = Position {
+            is_alive: false,
+            ..stake(9, Level::Darknet, "5000")
+        };
+        let position_store = Arc::new(MockPositionStore::new().with_positions(vec![
+            // Listed out of address order; dust must not depend on store order
+            stake(3, Level::Darknet, "400"),
+            stake(1, Level::Darknet, "100"),
+            stake(2, Level::Darknet, "200"),
+            stake(4, Level::Vault, "1000"),
+            stake(5, Level::Mainframe, "500"),
+            stake(6, Level::BlackIce, "700"),
+            dead,
+        ]));
+        let death_store = Arc::new(MockDeathStore::new());
+        let handler = DeathHandler::new(
+            Arc::clone(&death_store),
+            Arc::clone(&position_store),
+            Arc::new(MockCache::new()),
+        );
+
+        // Amounts in wei so that the split leaves dust
+        let event = ghost_core::CascadeDistributed {
+            sourceLevel: 4,
+            sameLevelAmount: U256::from(100),
+            upstreamAmount: U256::from(31),
+            burnAmount: U256::from(100),
+            protocolAmount: U256::from(33),
+        };
+        handler
+            .handle_cascade_distributed(event, test_metadata())
+            .await
+            .unwrap();
+
+        let shares: Vec<_> = death_store
+            .cascade_shares()
+            .into_iter()
+            .map(|share| {
+                assert_eq!(share.source_level, Level::Darknet);
+                (
+                    share.user_address.as_bytes()[19],
+                    share.level,
+                    share.amount.to_wei(DATA_TOKEN_DECIMALS).to::<u64>(),
+                )
+            })
+            .collect();
+        // 100 * 1/7, 2/7, 4/7 = 14.3, 28.6, 57.1: the largest remainder gets the dust
+        // 31 * 2/3, 1/3 = 20.7, 10.3
+        assert_eq!(
+            shares,
+            [
+                (1, Level::Darknet, 14),
+                (2, Level::Darknet, 29),
+                (3, Level::Darknet, 57),
+                (4, Level::Vault, 21),
+                (5, Level::Mainframe, 10),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn cascade_without_survivors_records_nothing() {
+        let (handler, death_store, _position_store, _cache) = create_handler();
+
+        let event = ghost_core::CascadeDistributed {
+            sourceLevel: 2,
+            sameLevelAmount: U256::from(100),
+            upstreamAmount: U256::from(100),
+            burnAmount: U256::from(100),
+            protocolAmount: U256::from(33),
+        };
+        handler
+            .handle_cascade_distributed(event, test_metadata())
+            .await
+            .unwrap();
+
+        assert!(death_store.cascade_shares().is_empty());
+    }
+
+    #[test]
+    fn pro_rata_split_never_exceeds_amount() {
+        let u = |values: &[u64]| values.iter().copied().map(U256::from).collect::<Vec<_>>();
+
+        assert_eq!(split_pro_rata(U256::from(100), &u(&[1, 2, 4])), u(&[14, 29, 57]));
+        // Equal remainders: the dust goes to the earliest weights
+        assert_eq!(split_pro_rata(U256::from(11), &u(&[1, 1, 1])), u(&[4, 4, 3]));
+        assert_eq!(split_pro_rata(U256::from(5), &u(&[0, 3])), u(&[0, 5]));
+        assert_eq!(split_pro_rata(U256::from(5), &u(&[0, 0])), u(&[0, 0]));
+        assert_eq!(split_pro_rata(U256::from(5), &[]), Vec::<U256>::new());
+
+        let weights = u(&[3, 7, 11, 13, 17, 19]);
+        for amount in 0..200_u64 {
+            let shares = split_pro_rata(U256::from(amount), &weights);
+            let sum = shares.iter().fold(U256::ZERO, |sum, share| sum + share);
+            assert_eq!(sum, U256::from(amount));
+        }
+    }
+
     #[tokio::test]
     async fn handle_emissions_added_succeeds() {
         let (handler, _death_store, _position_store, _cache) = create_handler();
@@ -825,6 +1240,56 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn record_scan_deaths_defers_until_deaths_submitted() {
+        let position = create_test_position(Level::Subnet);
+        let (handler, death_store, position_store, _cache) =
+            create_handler_with_position(position.clone());
+        let correlator = Arc::new(ScanCorrelator::new());
+        let handler = handler.with_correlator(Arc::clone(&correlator));
+        let meta = test_metadata();
+
+        let ids = handler
+            .record_scan_deaths(
+                Level::Subnet,
+                &[test_eth_address(), eth_address_from_byte(7)],
+                &meta,
+            )
+            .await
+            .unwrap();
+
+        // Only the victim with an active position is recorded
+        assert_eq!(ids.len(), 1);
+        assert_eq!(death_store.death_count(), 1);
+        let death = death_store.deaths.read().unwrap()[0].clone();
+        assert_eq!(death.scan_id, None);
+        assert_eq!(death.position_id, Some(position.id));
+        assert_eq!(correlator.deferred_count(), 1);
+
+        let position = position_store.get_position(&test_eth_address()).unwrap();
+        assert_eq!(position.exit_reason, Some(ExitReason::Traced));
+        assert_eq!(position_store.history_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn record_scan_deaths_stamps_known_scan() {
+        let (handler, death_store, _position_store, _cache) =
+            create_handler_with_position(create_test_position(Level::Subnet));
+        let correlator = Arc::new(ScanCorrelator::new());
+        let handler = handler.with_correlator(Arc::clone(&correlator));
+        let meta = test_metadata();
+        let scan = Uuid::new_v4();
+        correlator.submitted(meta.tx_hash, Level::Subnet, "3", scan);
+
+        handler
+            .record_scan_deaths(Level::Subnet, &[test_eth_address()], &meta)
+            .await
+            .unwrap();
+
+        assert_eq!(death_store.deaths.read().unwrap()[0].scan_id, Some(scan));
+        assert_eq!(correlator.deferred_count(), 0);
+    }
+
     #[test]
     fn to_level_valid_values() {
         assert!(DeathHandler::<MockDeathStore, MockPositionStore, MockCache>::to_level(0).is_ok());
@@ -243,6 +243,9 @@ mod tests {
             log_index: 0,
             timestamp: Utc::now(),
             contract: test_address(),
+            chain_id: 6343,
+            tx_function: None,
+            tx_from: None,
         }
     }
 
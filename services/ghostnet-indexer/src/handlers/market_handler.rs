@@ -30,6 +30,15 @@
 //! | `StreakRecord` (2) | Will anyone hit 20 survival streak? |
 //! | `SystemReset` (3) | Will the reset timer hit <1 hour? |
 //!
+//! # Odds
+//!
+//! Each bet is stored with the round's pools right before it, the probability
+//! they gave its side and the payout multiple it would have got had the round
+//! closed right after it; a resolved round gets the multiple its winners are
+//! paid and the house take. Pools are read from the round as stored, so bets
+//! must be handled in chain order, `(block_number, log_index)`, as the
+//! [`BlockProcessor`](crate::indexer::BlockProcessor) delivers them.
+//!
 //! # Architecture
 //!
 //! The handler follows hexagonal architecture principles:
@@ -51,7 +60,7 @@ use crate::ports::{Cache, MarketStore};
 use crate::types::entities::{Bet, Round};
 use crate::types::enums::{Level, RoundType};
 use crate::types::events::EventMetadata;
-use crate::types::primitives::{EthAddress, TokenAmount};
+use crate::types::primitives::{BlockNumber, EthAddress, TokenAmount};
 
 // ═══════════════════════════════════════════════════════════════════════════════
 // CONSTANTS
@@ -182,6 +191,8 @@ where
             outcome: None,
             resolve_time: None,
             total_burned: None,
+            payout_multiple_bps: None,
+            house_take: None,
         };
 
         // Save to database
@@ -205,7 +216,8 @@ where
 
     /// Handle bet placement.
     ///
-    /// Records the bet and updates the round's pool totals.
+    /// Records the bet, with the odds the round's pools gave right before
+    /// it, and updates the round's pool totals.
     #[instrument(skip(self, event, meta), fields(
         round_id = %event.roundId,
         user = %event.user,
@@ -240,6 +252,13 @@ where
             return Ok(());
         }
 
+        // Odds before the bet, and the payout had the round closed after it
+        let pools_before = round.pools();
+        let implied_probability_bps = pools_before.implied_probability_bps(is_over);
+        let payout_multiple_bps = pools_before
+            .with_bet(is_over, &amount)
+            .payout_multiple_bps(is_over);
+
         // Create bet record
         let bet = Bet {
             id: Uuid::new_v4(),
@@ -247,6 +266,12 @@ where
             user_address,
             amount: amount.clone(),
             is_over,
+            block_number: BlockNumber::new(meta.block_number),
+            log_index: meta.log_index,
+            placed_at: meta.timestamp,
+            pools_before,
+            implied_probability_bps,
+            payout_multiple_bps,
             is_claimed: false,
             winnings: None,
             claimed_at: None,
@@ -265,6 +290,8 @@ where
             user = %user_address,
             amount = %amount,
             side = side,
+            implied_probability_bps = ?implied_probability_bps,
+            payout_multiple_bps = ?payout_multiple_bps,
             block = meta.block_number,
             "Bet placed"
         );
@@ -274,7 +301,8 @@ where
 
     /// Handle round resolution.
     ///
-    /// Marks the round as resolved with the outcome.
+    /// Marks the round as resolved with the outcome, the payout multiple of
+    /// its winners and the house take.
     #[instrument(skip(self, event, meta), fields(
         round_id = %event.roundId,
         outcome = event.outcome
@@ -309,9 +337,8 @@ where
         }
 
         // Resolve the round
-        self.store
-            .resolve_round(&round_id, outcome, &burned)
-            .await?;
+        let settlement = existing.settle(outcome, &total_pot, &burned, meta.timestamp);
+        self.store.resolve_round(&round_id, &settlement).await?;
 
         // Invalidate cache
         self.cache.invalidate_all_positions();
@@ -322,6 +349,8 @@ where
             outcome = outcome_str,
             total_pot = %total_pot,
             burned = %burned,
+            payout_multiple_bps = ?settlement.payout_multiple_bps,
+            house_take = %settlement.house_take,
             block = meta.block_number,
             "Round resolved"
         );
@@ -370,7 +399,7 @@ where
 // ═══════════════════════════════════════════════════════════════════════════════
 
 #[cfg(test)]
-#[allow(clippy::unwrap_used)]
+#[allow(clippy::unwrap_used, clippy::significant_drop_tightening)]
 mod tests {
     use std::collections::HashMap;
     use std::sync::RwLock;
@@ -380,6 +409,7 @@ mod tests {
 
     use super::*;
     use crate::ports::MockCache;
+    use crate::types::entities::RoundSettlement;
     use crate::types::enums::Level;
 
     // ═══════════════════════════════════════════════════════════════════════════
@@ -453,15 +483,16 @@ mod tests {
         async fn resolve_round(
             &self,
             round_id: &str,
-            outcome: bool,
-            burned: &TokenAmount,
+            settlement: &RoundSettlement,
         ) -> Result<()> {
             let mut rounds = self.rounds.write().unwrap();
             if let Some(round) = rounds.get_mut(round_id) {
                 round.is_resolved = true;
-                round.outcome = Some(outcome);
-                round.resolve_time = Some(Utc::now());
-                round.total_burned = Some(burned.clone());
+                round.outcome = Some(settlement.outcome);
+                round.resolve_time = Some(settlement.resolved_at);
+                round.total_burned = Some(settlement.burned.clone());
+                round.payout_multiple_bps = settlement.payout_multiple_bps;
+                round.house_take = Some(settlement.house_take.clone());
                 Ok(())
             } else {
                 Err(crate::error::InfraError::NotFound.into())
@@ -565,6 +596,9 @@ mod tests {
             log_index: 0,
             timestamp: Utc::now(),
             contract: test_address(),
+            chain_id: 6343,
+            tx_function: None,
+            tx_from: None,
         }
     }
 
@@ -860,6 +894,85 @@ mod tests {
         assert!(round.is_resolved);
         assert_eq!(round.outcome, Some(true));
         assert_eq!(round.total_burned.as_ref().unwrap().to_string(), "7");
+
+        // 143 DATA after the rake, shared by the 100 bet on OVER
+        assert_eq!(round.payout_multiple_bps, Some(14_300));
+        assert_eq!(round.house_take.unwrap().to_string(), "7");
+    }
+
+    #[tokio::test]
+    async fn handle_bet_placed_snapshots_pools_in_log_order() {
+        let (handler, store, _cache) = create_handler();
+        for round_id in [1_u64, 2] {
+            let round_event = dead_pool::RoundCreated {
+                roundId: U256::from(round_id),
+                roundType: 0,
+                targetLevel: 0,
+                line: U256::from(10_u64),
+                deadline: 1_700_000_000,
+            };
+            handler
+                .handle_round_created(round_event, test_metadata())
+                .await
+                .unwrap();
+        }
+
+        // Bets on both rounds interleaved within one block, then the next
+        let bets = [
+            (1, true, 100, 1000, 3),
+            (2, false, 40, 1000, 5),
+            (1, false, 100, 1000, 8),
+            (1, true, 200, 1000, 12),
+            (2, true, 60, 1001, 0),
+        ];
+        for (round_id, is_over, amount, block, log_index) in bets {
+            let bet_event = dead_pool::BetPlaced {
+                roundId: U256::from(round_id),
+                user: test_address(),
+                isOver: is_over,
+                amount: U256::from(amount) * U256::from(10_u64).pow(U256::from(18_u64)),
+            };
+            let meta = EventMetadata {
+                block_number: block,
+                log_index,
+                ..test_metadata()
+            };
+            handler.handle_bet_placed(bet_event, meta).await.unwrap();
+        }
+
+        let snapshots: Vec<_> = store
+            .get_bets()
+            .iter()
+            .map(|bet| {
+                (
+                    bet.pools_before.over_pool.to_string(),
+                    bet.pools_before.under_pool.to_string(),
+                    bet.implied_probability_bps,
+                    bet.payout_multiple_bps,
+                )
+            })
+            .collect();
+        let snapshot = |over: &str, under: &str, probability, payout| {
+            (over.to_string(), under.to_string(), probability, payout)
+        };
+        assert_eq!(
+            snapshots,
+            [
+                // First bets of their rounds: no liquidity, their own stake back
+                snapshot("0", "0", None, Some(9_500)),
+                snapshot("0", "0", None, Some(9_500)),
+                // Round 1: 100 on OVER, then 200 more after an UNDER bet
+                snapshot("100", "0", Some(0), Some(19_000)),
+                snapshot("100", "100", Some(5_000), Some(12_666)),
+                // Round 2: 40 on UNDER, taking 95 of 100 with 60 on OVER
+                snapshot("0", "40", Some(0), Some(15_833)),
+            ]
+        );
+
+        let bets = store.get_bets();
+        assert_eq!((bets[3].block_number.value(), bets[3].log_index), (1000, 12));
+        let round = store.get_round("1").unwrap();
+        assert_eq!(round.pools(), bets[3].pools_before.with_bet(true, &bets[3].amount));
     }
 
     #[tokio::test]
@@ -31,6 +31,9 @@
 //! | [`FeeHandler`] | [`FeePort`] | Complete |
 //! | [`EmissionsHandler`] | [`EmissionsPort`] | Complete |
 //!
+//! The [`ScanHandler`] and [`DeathHandler`] can share a [`ScanCorrelator`] to
+//! link recorded deaths to the scan that caused them.
+//!
 //! # Usage
 //!
 //! ```ignore
@@ -57,6 +60,7 @@ mod emissions_handler;
 mod fee_handler;
 mod market_handler;
 mod position_handler;
+mod scan_correlator;
 mod scan_handler;
 mod token_handler;
 mod traits;
@@ -66,6 +70,7 @@ pub use emissions_handler::EmissionsHandler;
 pub use fee_handler::FeeHandler;
 pub use market_handler::MarketHandler;
 pub use position_handler::PositionHandler;
+pub use scan_correlator::ScanCorrelator;
 pub use scan_handler::ScanHandler;
 pub use token_handler::TokenHandler;
 pub use traits::{
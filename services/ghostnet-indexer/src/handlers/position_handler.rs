@@ -13,7 +13,11 @@
 //! - Receives decoded events from the `EventRouter`
 //! - Uses `PositionStore` port for persistence
 //! - Uses `Cache` port for cache invalidation
-//! - Uses `EventPublisher` port for streaming events
+//! - Uses `StatsSink` port (optional) for aggregate level statistics
+//! - Uses `BoostStore` port (optional) to track the boosts applied, and
+//!   weight each position's effective stake by its active yield multipliers
+//! - Writes the events to the event outbox (optional) with each change, for
+//!   the `OutboxRelay` to publish
 //!
 //! ```text
 //! ┌─────────────────────────────────────────────────────────────────┐
@@ -35,16 +39,23 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use tracing::{debug, info, instrument, warn};
 use uuid::Uuid;
 
 use crate::abi::ghost_core;
-use crate::error::{DomainError, Result};
+use crate::error::{DomainError, InfraError, Result};
 use crate::handlers::PositionPort;
-use crate::ports::{Cache, PositionStore};
-use crate::types::entities::{Position, PositionAction, PositionHistoryEntry};
-use crate::types::enums::{ExitReason, Level};
-use crate::types::events::EventMetadata;
+use crate::ports::{BoostStore, Cache, PositionStore, StatsSink};
+use crate::streaming::Topic;
+use crate::types::entities::{
+    Boost, LevelStatsDelta, OutboxEvent, Position, PositionAction, PositionHistoryEntry,
+};
+use crate::types::enums::{BoostType, ExitReason, Level};
+use crate::types::events::{
+    BoostAppliedEvent, EventMetadata, ExtractedEvent, GhostnetEvent, JackedInEvent,
+    PositionCulledEvent, StakeAddedEvent,
+};
 use crate::types::primitives::{BlockNumber, EthAddress, GhostStreak, TokenAmount};
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -62,12 +73,27 @@ const DATA_TOKEN_DECIMALS: u8 = 18;
 ///
 /// Processes events from the GhostCore contract and maintains
 /// position state in the database.
-#[derive(Debug)]
 pub struct PositionHandler<S, C> {
     /// Position store for persistence.
     store: Arc<S>,
     /// Cache for invalidation.
     cache: Arc<C>,
+    /// Sink for aggregate level statistics.
+    stats: Option<Arc<dyn StatsSink>>,
+    /// Whether events are written to the event outbox.
+    outbox: bool,
+    /// Store the applied boosts are saved in and read back from.
+    boosts: Option<Arc<dyn BoostStore>>,
+}
+
+impl<S, C> std::fmt::Debug for PositionHandler<S, C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PositionHandler")
+            .field("stats", &self.stats)
+            .field("outbox", &self.outbox)
+            .field("track_boosts", &self.boosts.is_some())
+            .finish_non_exhaustive()
+    }
 }
 
 impl<S, C> PositionHandler<S, C>
@@ -77,7 +103,60 @@ where
 {
     /// Create a new position handler.
     pub const fn new(store: Arc<S>, cache: Arc<C>) -> Self {
-        Self { store, cache }
+        Self {
+            store,
+            cache,
+            stats: None,
+            outbox: false,
+            boosts: None,
+        }
+    }
+
+    /// Record level statistics through the given sink.
+    #[must_use]
+    pub fn with_stats(mut self, stats: Arc<dyn StatsSink>) -> Self {
+        self.stats = Some(stats);
+        self
+    }
+
+    /// Write each event to the event outbox, in the same transaction as the
+    /// position change.
+    #[must_use]
+    pub const fn with_outbox(mut self) -> Self {
+        self.outbox = true;
+        self
+    }
+
+    /// Save each applied boost in `boosts`, for expiry tracking, and weight
+    /// effective stakes by the boosts saved there.
+    ///
+    /// Without a boost store, effective stakes equal the raw stake.
+    #[must_use]
+    pub fn with_boosts(mut self, boosts: Arc<dyn BoostStore>) -> Self {
+        self.boosts = Some(boosts);
+        self
+    }
+
+    /// Outbox entries for the event built by `event`, if the outbox is
+    /// enabled.
+    fn outbox_events(
+        &self,
+        event: impl FnOnce() -> Result<GhostnetEvent>,
+    ) -> Result<Vec<OutboxEvent>> {
+        if !self.outbox {
+            return Ok(Vec::new());
+        }
+        let event = event()?;
+        let entry = OutboxEvent::new(Topic::for_event(&event).as_str(), &event)
+            .map_err(InfraError::Serialization)?;
+        Ok(vec![entry])
+    }
+
+    /// Record a level stats delta, if a stats sink is configured.
+    fn record_stats(&self, level: Level, delta: LevelStatsDelta, meta: &EventMetadata) {
+        if let Some(stats) = &self.stats {
+            stats.record_level(level, delta, meta.timestamp);
+        }
     }
 
     /// Convert an Alloy address to our EthAddress type.
@@ -95,26 +174,81 @@ where
         Ok(Level::try_from(level)?)
     }
 
-    /// Record a history entry for position changes.
-    async fn record_history(
+    /// Weight the stake of `position` by its boosts active at `at`,
+    /// returning the effective stake it had before.
+    async fn weigh_stake(&self, position: &mut Position, at: DateTime<Utc>) -> Result<TokenAmount> {
+        let boosts = match &self.boosts {
+            Some(boosts) => boosts.get_active_boosts(&position.user_address, at).await?,
+            None => Vec::new(),
+        };
+        // Boosts of an earlier position of the address are not active
+        let boosts: Vec<_> = boosts
+            .into_iter()
+            .filter(|boost| boost.position_id == position.id)
+            .collect();
+        let previous = position.effective_stake.clone();
+        position.apply_boosts(&boosts);
+        Ok(previous)
+    }
+
+    /// Close `position` for `reason`, counting the scans it survived.
+    async fn close(
+        &self,
+        position: &mut Position,
+        reason: ExitReason,
+        meta: &EventMetadata,
+    ) -> Result<()> {
+        let scans_survived = self
+            .store
+            .count_scans_survived(
+                position.level,
+                position.created_at_block,
+                BlockNumber::new(meta.block_number),
+            )
+            .await?;
+        position.close(reason, meta.timestamp, scans_survived);
+        Ok(())
+    }
+
+    /// Record an exit of a position whose entry was never indexed.
+    ///
+    /// A backfill that starts mid-lifecycle sees exits without the matching
+    /// `JackedIn`. The exit is kept with unknown level and survival rather
+    /// than failing the event, and left out of the level statistics.
+    async fn record_unknown_entry_exit(
         &self,
         position: &Position,
         action: PositionAction,
-        amount_change: TokenAmount,
         meta: &EventMetadata,
+        events: &[OutboxEvent],
     ) -> Result<()> {
-        let entry = PositionHistoryEntry {
-            id: Uuid::new_v4(),
-            position_id: position.id,
-            user_address: position.user_address,
+        warn!(
+            user = %position.user_address,
+            reason = ?position.exit_reason,
+            block = meta.block_number,
+            "Exit without a recorded entry, survival unknown"
+        );
+
+        let entry = Self::history_entry(position, action, position.amount.clone(), meta);
+        self.store.save_position_with_history(position, &entry, events).await?;
+        self.cache.invalidate_position(&position.user_address);
+        Ok(())
+    }
+
+    /// History entry for `action` on `position`, as changed by the event.
+    fn history_entry(
+        position: &Position,
+        action: PositionAction,
+        amount_change: TokenAmount,
+        meta: &EventMetadata,
+    ) -> PositionHistoryEntry {
+        PositionHistoryEntry::new(
+            position,
             action,
             amount_change,
-            new_total: position.amount.clone(),
-            block_number: BlockNumber::new(meta.block_number),
-            timestamp: meta.timestamp,
-        };
-
-        self.store.record_history(&entry).await
+            BlockNumber::new(meta.block_number),
+            meta.timestamp,
+        )
     }
 }
 
@@ -150,22 +284,21 @@ where
                 "Closing existing position due to new JackedIn event"
             );
 
-            // Close the existing position
+            // Close the existing position; its stake moves to the new one
             existing.is_alive = false;
             existing.exit_reason = Some(ExitReason::Superseded);
             existing.exit_timestamp = Some(meta.timestamp);
             existing.updated_at = meta.timestamp;
 
-            self.store.save_position(&existing).await?;
-
-            // Record history for the closed position
-            self.record_history(
+            let entry = Self::history_entry(
                 &existing,
                 PositionAction::Superseded,
-                TokenAmount::zero(), // No amount change, just closure
+                existing.amount.clone(),
                 &meta,
-            )
-            .await?;
+            );
+            self.store.save_position_with_history(&existing, &entry, &[]).await?;
+
+            self.record_stats(existing.level, LevelStatsDelta::closed(&existing), &meta);
         }
 
         // Create new position
@@ -174,6 +307,7 @@ where
             user_address,
             level,
             amount: amount.clone(),
+            effective_stake: amount.clone(),
             reward_debt: TokenAmount::zero(),
             entry_timestamp: meta.timestamp,
             last_add_timestamp: None,
@@ -182,18 +316,28 @@ where
             is_extracted: false,
             exit_reason: None,
             exit_timestamp: None,
+            survival_seconds: None,
+            scans_survived: None,
             extracted_amount: None,
             extracted_rewards: None,
             created_at_block: BlockNumber::new(meta.block_number),
             updated_at: meta.timestamp,
         };
 
-        // Save to database
-        self.store.save_position(&position).await?;
-
-        // Record history
-        self.record_history(&position, PositionAction::JackedIn, amount.clone(), &meta)
-            .await?;
+        // Save to database together with its first history entry
+        let entry = Self::history_entry(&position, PositionAction::JackedIn, amount.clone(), &meta);
+        let events = self.outbox_events(|| {
+            Ok(GhostnetEvent::JackedIn(JackedInEvent {
+                meta: meta.clone(),
+                user: event.user,
+                amount: event.amount,
+                level,
+                new_total: event.newTotal,
+            }))
+        })?;
+        self.store.save_position_with_history(&position, &entry, &events).await?;
+
+        self.record_stats(level, LevelStatsDelta::opened(&position), &meta);
 
         // Invalidate cache (sync operation - no await)
         self.cache.invalidate_position(&position.user_address);
@@ -209,7 +353,8 @@ where
 
     /// Handle stake addition to existing position (StakeAdded event).
     ///
-    /// Updates the position's amount and records the addition time.
+    /// Updates the position's amount and records the addition time. The new
+    /// total is weighted by the boosts still active on the position.
     #[instrument(skip(self, event, meta), fields(user = %event.user))]
     async fn handle_stake_added(
         &self,
@@ -228,21 +373,34 @@ where
             .ok_or_else(|| DomainError::PositionNotFound(user_address.to_string()))?;
 
         // Update position
-        position.amount = new_total;
+        let previous_amount = std::mem::replace(&mut position.amount, new_total);
+        let previous_effective = self.weigh_stake(&mut position, meta.timestamp).await?;
         position.last_add_timestamp = Some(meta.timestamp);
         position.updated_at = meta.timestamp;
 
-        // Save to database
-        self.store.save_position(&position).await?;
-
-        // Record history
-        self.record_history(
+        // Save to database with history
+        let entry = Self::history_entry(
             &position,
             PositionAction::StakeAdded,
             added_amount.clone(),
             &meta,
-        )
-        .await?;
+        );
+        let events = self.outbox_events(|| {
+            Ok(GhostnetEvent::StakeAdded(StakeAddedEvent {
+                meta: meta.clone(),
+                user: event.user,
+                amount: event.amount,
+                new_total: event.newTotal,
+            }))
+        })?;
+        self.store.save_position_with_history(&position, &entry, &events).await?;
+
+        let mut delta = LevelStatsDelta::stake_changed(previous_amount, position.amount.clone());
+        delta.merge(LevelStatsDelta::effective_stake_changed(
+            previous_effective,
+            position.effective_stake.clone(),
+        ));
+        self.record_stats(position.level, delta, &meta);
 
         // Invalidate cache (sync operation - no await)
         self.cache.invalidate_position(&user_address);
@@ -251,6 +409,7 @@ where
             position_id = %position.id,
             added = %added_amount,
             new_total = %position.amount,
+            effective_stake = %position.effective_stake,
             "Stake added to position"
         );
 
@@ -259,7 +418,8 @@ where
 
     /// Handle position extraction (Extracted event).
     ///
-    /// Marks the position as extracted and records the extracted amounts.
+    /// Marks the position as extracted and records the extracted amounts and
+    /// survival time.
     #[instrument(skip(self, event, meta), fields(user = %event.user))]
     async fn handle_extracted(
         &self,
@@ -270,32 +430,47 @@ where
         // Note: In the contract, `amount` is the principal returned
         let principal = Self::to_token_amount(&event.amount);
         let rewards = Self::to_token_amount(&event.rewards);
-
-        // Get existing position
-        let mut position = self
-            .store
-            .get_active_position(&user_address)
-            .await?
-            .ok_or_else(|| DomainError::PositionNotFound(user_address.to_string()))?;
-
-        // Calculate total extracted for history
-        let total_extracted = principal.saturating_add(&rewards);
+        let events = self.outbox_events(|| {
+            Ok(GhostnetEvent::Extracted(ExtractedEvent {
+                meta: meta.clone(),
+                user: event.user,
+                amount: event.amount,
+                rewards: event.rewards,
+            }))
+        })?;
+
+        let Some(mut position) = self.store.get_active_position(&user_address).await? else {
+            let mut position = Position::unknown_entry(
+                user_address,
+                principal.clone(),
+                ExitReason::Extracted,
+                meta.timestamp,
+                BlockNumber::new(meta.block_number),
+            );
+            position.extracted_amount = Some(principal);
+            position.extracted_rewards = Some(rewards);
+            return self
+                .record_unknown_entry_exit(&position, PositionAction::Extracted, &meta, &events)
+                .await;
+        };
 
         // Update position
-        position.is_alive = false;
+        self.close(&mut position, ExitReason::Extracted, &meta).await?;
         position.is_extracted = true;
-        position.exit_reason = Some(ExitReason::Extracted);
-        position.exit_timestamp = Some(meta.timestamp);
         position.extracted_amount = Some(principal.clone());
         position.extracted_rewards = Some(rewards.clone());
-        position.updated_at = meta.timestamp;
 
-        // Save to database
-        self.store.save_position(&position).await?;
+        // Save to database with history (the whole stake leaves the position;
+        // principal and rewards paid out are on the position row)
+        let entry = Self::history_entry(
+            &position,
+            PositionAction::Extracted,
+            position.amount.clone(),
+            &meta,
+        );
+        self.store.save_position_with_history(&position, &entry, &events).await?;
 
-        // Record history (negative amount since funds are leaving)
-        self.record_history(&position, PositionAction::Extracted, total_extracted, &meta)
-            .await?;
+        self.record_stats(position.level, LevelStatsDelta::extracted(&position), &meta);
 
         // Invalidate cache (sync operation - no await)
         self.cache.invalidate_position(&user_address);
@@ -312,8 +487,10 @@ where
 
     /// Handle boost application (BoostApplied event).
     ///
-    /// Records that a boost was applied from a mini-game.
-    /// Note: Boost effects are typically tracked separately, this just logs the event.
+    /// Records that a boost was applied from a mini-game in the position's
+    /// history, and saves the boost if a boost store is configured. A yield
+    /// multiplier reweights the position's effective stake then; other boost
+    /// effects are not tracked on the position itself.
     #[instrument(skip(self, event, meta), fields(user = %event.user, boost_type = event.boostType))]
     async fn handle_boost_applied(
         &self,
@@ -325,19 +502,63 @@ where
         let expiry = event.expiry;
 
         // Get existing position (boost requires active position)
-        let position = self
+        let mut position = self
             .store
             .get_active_position(&user_address)
             .await?
             .ok_or_else(|| DomainError::PositionNotFound(user_address.to_string()))?;
 
-        // For now, just log the boost application
-        // In Phase 5, we'll add a separate BoostStore for tracking active boosts
+        let applied = || -> Result<BoostAppliedEvent> {
+            Ok(BoostAppliedEvent {
+                meta: meta.clone(),
+                user: event.user,
+                boost_type: BoostType::try_from(event.boostType)?,
+                value_bps,
+                expiry,
+            })
+        };
+        // Saved first, so a replayed log weighs the stake the same
+        if let Some(boosts) = &self.boosts {
+            boosts.save_boost(&Boost::applied(&position, &applied()?)).await?;
+        }
+        let previous_effective = self.weigh_stake(&mut position, meta.timestamp).await?;
+        let reweighted = position.effective_stake != previous_effective;
+        if reweighted {
+            position.updated_at = meta.timestamp;
+        }
+
+        // Unless the stake was reweighted the position row is unchanged, so
+        // only the history is written, unless the event goes to the outbox
+        // with it
+        let entry = Self::history_entry(
+            &position,
+            PositionAction::BoostApplied,
+            TokenAmount::zero(),
+            &meta,
+        );
+        let events = self.outbox_events(|| applied().map(GhostnetEvent::BoostApplied))?;
+        if events.is_empty() && !reweighted {
+            self.store.append_history(&entry).await?;
+        } else {
+            self.store.save_position_with_history(&position, &entry, &events).await?;
+        }
+        if reweighted {
+            self.record_stats(
+                position.level,
+                LevelStatsDelta::effective_stake_changed(
+                    previous_effective,
+                    position.effective_stake.clone(),
+                ),
+                &meta,
+            );
+        }
+
         debug!(
             position_id = %position.id,
             boost_type = event.boostType,
             value_bps,
             expiry,
+            effective_stake = %position.effective_stake,
             block = meta.block_number,
             "Boost applied to position"
         );
@@ -350,8 +571,9 @@ where
 
     /// Handle position culling (PositionCulled event).
     ///
-    /// Marks the position as dead due to level capacity overflow.
-    /// The victim loses a penalty, gets some amount returned, and makes room for new entrant.
+    /// Marks the position as dead due to level capacity overflow and records
+    /// its survival time. The victim loses a penalty, gets some amount
+    /// returned, and makes room for new entrant.
     #[instrument(skip(self, event, meta), fields(victim = %event.victim, new_entrant = %event.newEntrant))]
     async fn handle_position_culled(
         &self,
@@ -361,32 +583,44 @@ where
         let victim_address = Self::to_eth_address(&event.victim);
         let penalty_amount = Self::to_token_amount(&event.penaltyAmount);
         // TODO: Track returned_amount in Position.extracted_amount when we add partial return support
-        let _returned_amount = Self::to_token_amount(&event.returnedAmount);
-
-        // Get existing position
-        let mut position = self
-            .store
-            .get_active_position(&victim_address)
-            .await?
-            .ok_or_else(|| DomainError::PositionNotFound(victim_address.to_string()))?;
+        let returned_amount = Self::to_token_amount(&event.returnedAmount);
+        let events = self.outbox_events(|| {
+            Ok(GhostnetEvent::PositionCulled(PositionCulledEvent {
+                meta: meta.clone(),
+                victim: event.victim,
+                penalty_amount: event.penaltyAmount,
+                returned_amount: event.returnedAmount,
+                new_entrant: event.newEntrant,
+            }))
+        })?;
+
+        let Some(mut position) = self.store.get_active_position(&victim_address).await? else {
+            let position = Position::unknown_entry(
+                victim_address,
+                penalty_amount.saturating_add(&returned_amount),
+                ExitReason::Culled,
+                meta.timestamp,
+                BlockNumber::new(meta.block_number),
+            );
+            return self
+                .record_unknown_entry_exit(&position, PositionAction::Culled, &meta, &events)
+                .await;
+        };
 
         // Update position
-        position.is_alive = false;
-        position.exit_reason = Some(ExitReason::Culled);
-        position.exit_timestamp = Some(meta.timestamp);
-        position.updated_at = meta.timestamp;
+        self.close(&mut position, ExitReason::Culled, &meta).await?;
 
-        // Save to database
-        self.store.save_position(&position).await?;
-
-        // Record history (the penalty amount is what was lost)
-        self.record_history(
+        // Save to database with history (the whole stake leaves the position:
+        // the penalty is lost, the rest returned)
+        let entry = Self::history_entry(
             &position,
             PositionAction::Culled,
-            penalty_amount.clone(),
+            position.amount.clone(),
             &meta,
-        )
-        .await?;
+        );
+        self.store.save_position_with_history(&position, &entry, &events).await?;
+
+        self.record_stats(position.level, LevelStatsDelta::culled(&position), &meta);
 
         // Invalidate cache (sync operation - no await)
         self.cache.invalidate_position(&victim_address);
@@ -407,7 +641,7 @@ where
 // ═══════════════════════════════════════════════════════════════════════════════
 
 #[cfg(test)]
-#[allow(clippy::unwrap_used)]
+#[allow(clippy::unwrap_used, clippy::significant_drop_tightening)]
 mod tests {
     use std::collections::HashMap;
     use std::sync::{Arc, RwLock};
@@ -418,7 +652,7 @@ mod tests {
     use super::*;
     use crate::abi::ghost_core;
     use crate::ports::MockCache;
-    use crate::types::entities::PositionHistoryEntry;
+    use crate::types::entities::{HistoryCursor, Page, PositionFilter, PositionHistoryEntry};
     use crate::types::enums::Level;
     use crate::types::primitives::EthAddress;
 
@@ -431,6 +665,10 @@ mod tests {
     struct MockPositionStore {
         positions: RwLock<HashMap<EthAddress, Position>>,
         history: RwLock<Vec<PositionHistoryEntry>>,
+        /// Events written to the outbox, in order.
+        outbox: RwLock<Vec<OutboxEvent>>,
+        /// Level and block of each finalized scan.
+        finalized_scans: RwLock<Vec<(Level, u64)>>,
     }
 
     impl MockPositionStore {
@@ -474,12 +712,40 @@ mod tests {
             Ok(vec![])
         }
 
-        async fn record_history(&self, entry: &PositionHistoryEntry) -> Result<()> {
+        async fn append_history(&self, entry: &PositionHistoryEntry) -> Result<()> {
             let mut history = self.history.write().unwrap();
             history.push(entry.clone());
             Ok(())
         }
 
+        async fn save_position_with_history(
+            &self,
+            position: &Position,
+            entry: &PositionHistoryEntry,
+            events: &[OutboxEvent],
+        ) -> Result<()> {
+            self.save_position(position).await?;
+            self.outbox.write().unwrap().extend_from_slice(events);
+            self.append_history(entry).await
+        }
+
+        async fn get_history(
+            &self,
+            address: &EthAddress,
+            limit: u32,
+            before: Option<HistoryCursor>,
+        ) -> Result<Vec<PositionHistoryEntry>> {
+            let history = self.history.read().unwrap();
+            Ok(history
+                .iter()
+                .rev()
+                .filter(|e| e.user_address == *address)
+                .filter(|e| before.is_none_or(|c| (e.timestamp, e.id) < (c.timestamp, c.id)))
+                .take(limit as usize)
+                .cloned()
+                .collect())
+        }
+
         async fn get_position_by_id(&self, _id: &Uuid) -> Result<Option<Position>> {
             Ok(None)
         }
@@ -488,9 +754,94 @@ mod tests {
             Ok(vec![])
         }
 
+        async fn query(&self, _: &PositionFilter, _: u32) -> Result<Page<Position>> {
+            Ok(Page {
+                items: vec![],
+                next_cursor: None,
+            })
+        }
+
         async fn count_positions_by_level(&self, _level: Level) -> Result<u32> {
             Ok(0)
         }
+
+        #[allow(clippy::cast_possible_truncation)]
+        async fn count_scans_survived(
+            &self,
+            level: Level,
+            entered: BlockNumber,
+            exited: BlockNumber,
+        ) -> Result<u32> {
+            let scans = self.finalized_scans.read().unwrap();
+            Ok(scans
+                .iter()
+                .filter(|(l, block)| {
+                    *l == level && *block > entered.value() && *block < exited.value()
+                })
+                .count() as u32)
+        }
+    }
+
+    /// Boost store keeping one boost per `BoostApplied` log.
+    #[derive(Debug, Default)]
+    struct MemoryBoosts {
+        boosts: RwLock<Vec<Boost>>,
+    }
+
+    #[async_trait]
+    impl BoostStore for MemoryBoosts {
+        async fn save_boost(&self, boost: &Boost) -> Result<()> {
+            let mut boosts = self.boosts.write().unwrap();
+            if !boosts
+                .iter()
+                .any(|b| (b.block_number, b.log_index) == (boost.block_number, boost.log_index))
+            {
+                boosts.push(boost.clone());
+            }
+            Ok(())
+        }
+
+        async fn get_active_boosts(
+            &self,
+            address: &EthAddress,
+            now: chrono::DateTime<Utc>,
+        ) -> Result<Vec<Boost>> {
+            let boosts = self.boosts.read().unwrap();
+            Ok(boosts
+                .iter()
+                .filter(|b| b.user_address == *address && b.is_active(now))
+                .cloned()
+                .collect())
+        }
+
+        async fn get_expiring_boosts(
+            &self,
+            _now: chrono::DateTime<Utc>,
+            _before: chrono::DateTime<Utc>,
+            _limit: u32,
+        ) -> Result<Vec<Boost>> {
+            Ok(Vec::new())
+        }
+
+        async fn get_lapsed_boosts(
+            &self,
+            _now: chrono::DateTime<Utc>,
+            _limit: u32,
+        ) -> Result<Vec<crate::types::entities::LapsedBoost>> {
+            Ok(Vec::new())
+        }
+
+        async fn expire_boosts(&self, _expired: &[(Uuid, chrono::DateTime<Utc>)]) -> Result<u64> {
+            Ok(0)
+        }
+
+        async fn reweigh_positions(
+            &self,
+            _positions: &[Uuid],
+            _now: chrono::DateTime<Utc>,
+        ) -> Result<Vec<crate::types::entities::EffectiveStakeChange>> {
+            Ok(Vec::new())
+        }
     }
 
     // ═══════════════════════════════════════════════════════════════════════════
@@ -512,6 +863,18 @@ mod tests {
             log_index: 0,
             timestamp: Utc::now(),
             contract: test_address(),
+            chain_id: 6343,
+            tx_function: None,
+            tx_from: None,
+        }
+    }
+
+    /// Metadata for an event `secs` seconds and `blocks` blocks after `base`.
+    fn later_metadata(base: &EventMetadata, secs: i64, blocks: u64) -> EventMetadata {
+        EventMetadata {
+            block_number: base.block_number + blocks,
+            timestamp: base.timestamp + chrono::TimeDelta::seconds(secs),
+            ..base.clone()
         }
     }
 
@@ -561,6 +924,48 @@ mod tests {
         assert_eq!(position.level, Level::Subnet);
     }
 
+    #[tokio::test]
+    async fn outbox_events_are_saved_with_the_change() {
+        let (handler, store, _cache) = create_handler();
+        let meta = test_metadata();
+        let jacked_in = ghost_core::JackedIn {
+            user: test_address(),
+            amount: U256::from(100_u64),
+            level: 1,
+            newTotal: U256::from(100_u64),
+        };
+
+        // Nothing is written to the outbox unless enabled
+        handler.handle_jacked_in(jacked_in.clone(), meta.clone()).await.unwrap();
+        assert!(store.outbox.read().unwrap().is_empty());
+
+        let handler = handler.with_outbox();
+        handler.handle_jacked_in(jacked_in, meta.clone()).await.unwrap();
+        let boost = ghost_core::BoostApplied {
+            user: test_address(),
+            boostType: 0,
+            valueBps: 500,
+            expiry: 0,
+        };
+        handler.handle_boost_applied(boost, later_metadata(&meta, 10, 1)).await.unwrap();
+        let extracted = ghost_core::Extracted {
+            user: test_address(),
+            amount: U256::from(100_u64),
+            rewards: U256::from(5_u64),
+        };
+        handler.handle_extracted(extracted, later_metadata(&meta, 20, 2)).await.unwrap();
+
+        let outbox = store.outbox.read().unwrap();
+        let types: Vec<&str> = outbox.iter().map(|e| e.event_type.as_str()).collect();
+        assert_eq!(types, ["JackedIn", "BoostApplied", "Extracted"]);
+        let aggregate = format!("position:{:#x}", test_address());
+        assert!(outbox.iter().all(|e| e.topic == "positions" && e.aggregate == aggregate));
+        assert_eq!(outbox[2].block_number, BlockNumber::new(meta.block_number + 2));
+
+        let payload: GhostnetEvent = serde_json::from_slice(&outbox[2].payload).unwrap();
+        assert!(matches!(payload, GhostnetEvent::Extracted(e) if e.rewards == U256::from(5_u64)));
+    }
+
     #[tokio::test]
     async fn handle_jacked_in_closes_existing_position() {
         let (handler, store, _cache) = create_handler();
@@ -672,18 +1077,27 @@ mod tests {
             level: 3,
             newTotal: U256::from(1000_u64) * U256::from(10_u64).pow(U256::from(18_u64)),
         };
+        let entered = test_metadata();
         handler
-            .handle_jacked_in(jacked_in, test_metadata())
+            .handle_jacked_in(jacked_in, entered.clone())
             .await
             .unwrap();
 
+        // Two Subnet scans while the position is open, one at another level
+        store.finalized_scans.write().unwrap().extend([
+            (Level::Subnet, 1010),
+            (Level::Subnet, 1020),
+            (Level::Darknet, 1015),
+        ]);
+
         // Extract
         let extracted = ghost_core::Extracted {
             user: test_address(),
             amount: U256::from(900_u64) * U256::from(10_u64).pow(U256::from(18_u64)), // principal
             rewards: U256::from(100_u64) * U256::from(10_u64).pow(U256::from(18_u64)),
         };
-        let result = handler.handle_extracted(extracted, test_metadata()).await;
+        let exited = later_metadata(&entered, 3600, 30);
+        let result = handler.handle_extracted(extracted, exited.clone()).await;
         assert!(result.is_ok());
 
         // Verify position is closed
@@ -691,8 +1105,42 @@ mod tests {
         assert!(!position.is_alive);
         assert!(position.is_extracted);
         assert_eq!(position.exit_reason, Some(ExitReason::Extracted));
+        assert_eq!(position.exit_timestamp, Some(exited.timestamp));
         assert!(position.extracted_amount.is_some());
         assert!(position.extracted_rewards.is_some());
+        assert_eq!(position.survival_seconds, Some(3600));
+        assert_eq!(position.scans_survived, Some(2));
+    }
+
+    #[tokio::test]
+    async fn handle_extracted_without_entry_records_unknown_survival() {
+        let (handler, store, _cache) = create_handler();
+        let user_address = EthAddress::new(test_address().0.0);
+
+        // Backfill started after the JackedIn
+        let extracted = ghost_core::Extracted {
+            user: test_address(),
+            amount: U256::from(900_u64) * U256::from(10_u64).pow(U256::from(18_u64)),
+            rewards: U256::from(100_u64) * U256::from(10_u64).pow(U256::from(18_u64)),
+        };
+        handler
+            .handle_extracted(extracted, test_metadata())
+            .await
+            .unwrap();
+
+        let position = store.get_position(&user_address).unwrap();
+        assert!(!position.is_active());
+        assert!(position.is_extracted);
+        assert_eq!(position.level, Level::None);
+        assert_eq!(position.exit_reason, Some(ExitReason::Extracted));
+        assert_eq!(position.extracted_amount.unwrap().to_string(), "900");
+        assert_eq!(position.extracted_rewards.unwrap().to_string(), "100");
+        assert_eq!(position.survival_seconds, None);
+        assert_eq!(position.scans_survived, None);
+
+        let history = store.get_history(&user_address, 10, None).await.unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].action, PositionAction::Extracted);
     }
 
     #[tokio::test]
@@ -707,10 +1155,16 @@ mod tests {
             level: 5, // BlackIce
             newTotal: U256::from(1000_u64) * U256::from(10_u64).pow(U256::from(18_u64)),
         };
+        let entered = test_metadata();
         handler
-            .handle_jacked_in(jacked_in, test_metadata())
+            .handle_jacked_in(jacked_in, entered.clone())
             .await
             .unwrap();
+        store
+            .finalized_scans
+            .write()
+            .unwrap()
+            .push((Level::BlackIce, 1001));
 
         // Cull the position
         let culled = ghost_core::PositionCulled {
@@ -722,7 +1176,7 @@ mod tests {
                 .unwrap(),
         };
         let result = handler
-            .handle_position_culled(culled, test_metadata())
+            .handle_position_culled(culled, later_metadata(&entered, 90, 5))
             .await;
         assert!(result.is_ok());
 
@@ -730,6 +1184,212 @@ mod tests {
         let position = store.get_position(&user_address).unwrap();
         assert!(!position.is_alive);
         assert_eq!(position.exit_reason, Some(ExitReason::Culled));
+        assert_eq!(position.survival_seconds, Some(90));
+        assert_eq!(position.scans_survived, Some(1));
+
+        // The whole stake leaves the position
+        let history = store.get_history(&user_address, 1, None).await.unwrap();
+        assert_eq!(history[0].action, PositionAction::Culled);
+        assert_eq!(history[0].amount_change, position.amount);
+        assert_eq!(history[0].new_total, TokenAmount::zero());
+    }
+
+    #[tokio::test]
+    async fn handle_position_culled_without_entry_records_unknown_survival() {
+        let (handler, store, _cache) = create_handler();
+        let user_address = EthAddress::new(test_address().0.0);
+
+        let culled = ghost_core::PositionCulled {
+            victim: test_address(),
+            penaltyAmount: U256::from(100_u64) * U256::from(10_u64).pow(U256::from(18_u64)),
+            returnedAmount: U256::from(900_u64) * U256::from(10_u64).pow(U256::from(18_u64)),
+            newEntrant: "0xabcdef0123456789abcdef0123456789abcdef01"
+                .parse()
+                .unwrap(),
+        };
+        handler
+            .handle_position_culled(culled, test_metadata())
+            .await
+            .unwrap();
+
+        let position = store.get_position(&user_address).unwrap();
+        assert!(!position.is_active());
+        assert!(!position.is_extracted);
+        assert_eq!(position.exit_reason, Some(ExitReason::Culled));
+        // Penalty plus returned amount is the stake that left
+        assert_eq!(position.amount.to_string(), "1000");
+        assert_eq!(position.survival_seconds, None);
+        assert_eq!(position.scans_survived, None);
+    }
+
+    #[tokio::test]
+    async fn history_reconciles_with_position_lifecycle() {
+        let (handler, store, _cache) = create_handler();
+        let user_address = EthAddress::new(test_address().0.0);
+        let tokens = |n: u64| U256::from(n) * U256::from(10_u64).pow(U256::from(18_u64));
+
+        let jacked_in = ghost_core::JackedIn {
+            user: test_address(),
+            amount: tokens(1000),
+            level: 3,
+            newTotal: tokens(1000),
+        };
+        handler
+            .handle_jacked_in(jacked_in, test_metadata())
+            .await
+            .unwrap();
+        let stake_added = ghost_core::StakeAdded {
+            user: test_address(),
+            amount: tokens(500),
+            newTotal: tokens(1500),
+        };
+        handler
+            .handle_stake_added(stake_added, test_metadata())
+            .await
+            .unwrap();
+        let boost = ghost_core::BoostApplied {
+            user: test_address(),
+            boostType: 1,
+            valueBps: 500,
+            expiry: 1_900_000_000,
+        };
+        handler
+            .handle_boost_applied(boost, test_metadata())
+            .await
+            .unwrap();
+        let extracted = ghost_core::Extracted {
+            user: test_address(),
+            amount: tokens(1500),
+            rewards: tokens(100),
+        };
+        handler
+            .handle_extracted(extracted, test_metadata())
+            .await
+            .unwrap();
+
+        let position = store.get_position(&user_address).unwrap();
+        let mut history = store.get_history(&user_address, 10, None).await.unwrap();
+        history.reverse();
+
+        let actions: Vec<_> = history.iter().map(|e| e.action).collect();
+        assert_eq!(
+            actions,
+            [
+                PositionAction::JackedIn,
+                PositionAction::StakeAdded,
+                PositionAction::BoostApplied,
+                PositionAction::Extracted,
+            ]
+        );
+        let totals: Vec<_> = history.iter().map(|e| e.new_total.to_string()).collect();
+        assert_eq!(totals, ["1000", "1500", "1500", "0"]);
+
+        // Stake added over the lifecycle is what the position held when it closed
+        let staked = history
+            .iter()
+            .filter(|e| e.action.adds_stake())
+            .fold(TokenAmount::zero(), |total, e| {
+                total.saturating_add(&e.amount_change)
+            });
+        assert_eq!(staked, position.amount);
+        let last = history.last().unwrap();
+        assert_eq!(last.amount_change, position.amount);
+        assert!(last.action.closes_position() && !position.is_alive);
+
+        for entry in &history {
+            assert_eq!(entry.position_id, position.id);
+            assert_eq!(entry.level, position.level);
+            assert_eq!(entry.ghost_streak, position.ghost_streak);
+        }
+    }
+
+    #[tokio::test]
+    #[allow(clippy::panic)]
+    async fn handle_boost_applied_saves_the_boost_once() {
+        let (handler, store, _cache) = create_handler();
+        let boosts = Arc::new(MemoryBoosts::default());
+        let handler = handler.with_boosts(boosts.clone());
+        let meta = test_metadata();
+        let jacked_in = ghost_core::JackedIn {
+            user: test_address(),
+            amount: U256::from(1000_u64),
+            level: 3,
+            newTotal: U256::from(1000_u64),
+        };
+        handler.handle_jacked_in(jacked_in, meta.clone()).await.unwrap();
+
+        let boost = ghost_core::BoostApplied {
+            user: test_address(),
+            boostType: 0,
+            valueBps: 2500,
+            expiry: 1_900_000_000,
+        };
+        let applied = later_metadata(&meta, 10, 1);
+        handler.handle_boost_applied(boost.clone(), applied.clone()).await.unwrap();
+        // A replayed log is not a second boost
+        handler.handle_boost_applied(boost, applied).await.unwrap();
+
+        let user_address = EthAddress::new(test_address().0.0);
+        let position = store.get_position(&user_address).unwrap();
+        let saved = boosts.boosts.read().unwrap().clone();
+        let [boost] = saved.as_slice() else {
+            panic!("expected one boost, got {saved:?}");
+        };
+        assert_eq!(boost.position_id, position.id);
+        assert_eq!(boost.boost_type, BoostType::DeathReduction);
+        assert_eq!(boost.value_bps, 2500);
+        assert_eq!(boost.expiry.timestamp(), 1_900_000_000);
+        assert_eq!(boost.block_number.value(), 1001);
+        assert_eq!(boost.expired_at, None);
+    }
+
+    #[tokio::test]
+    async fn yield_multipliers_weight_the_effective_stake() {
+        let (handler, store, _cache) = create_handler();
+        let handler = handler.with_boosts(Arc::new(MemoryBoosts::default()));
+        let tokens = |n: u64| U256::from(n) * U256::from(10_u64).pow(U256::from(18_u64));
+        let user_address = EthAddress::new(test_address().0.0);
+        let stakes = || {
+            let position = store.get_position(&user_address).unwrap();
+            (position.amount.to_string(), position.effective_stake.to_string())
+        };
+        let boost = |boost_type, value_bps| ghost_core::BoostApplied {
+            user: test_address(),
+            boostType: boost_type,
+            valueBps: value_bps,
+            expiry: 1_900_000_000,
+        };
+        let meta = test_metadata();
+        let jacked_in = ghost_core::JackedIn {
+            user: test_address(),
+            amount: tokens(1000),
+            level: 3,
+            newTotal: tokens(1000),
+        };
+        handler.handle_jacked_in(jacked_in, meta.clone()).await.unwrap();
+        assert_eq!(stakes(), ("1000".into(), "1000".into()));
+
+        // A death reduction leaves the stake as it is
+        handler.handle_boost_applied(boost(0, 2500), later_metadata(&meta, 10, 1)).await.unwrap();
+        assert_eq!(stakes().1, "1000");
+
+        handler.handle_boost_applied(boost(1, 2000), later_metadata(&meta, 20, 2)).await.unwrap();
+        assert_eq!(stakes().1, "1200");
+
+        // Multipliers stack additively, and a replayed log is not a second one
+        let stacked = later_metadata(&meta, 30, 3);
+        handler.handle_boost_applied(boost(1, 3000), stacked.clone()).await.unwrap();
+        handler.handle_boost_applied(boost(1, 3000), stacked).await.unwrap();
+        assert_eq!(stakes().1, "1500");
+
+        // Stake added mid-boost is weighted by the multipliers still active
+        let stake_added = ghost_core::StakeAdded {
+            user: test_address(),
+            amount: tokens(500),
+            newTotal: tokens(1500),
+        };
+        handler.handle_stake_added(stake_added, later_metadata(&meta, 40, 4)).await.unwrap();
+        assert_eq!(stakes(), ("1500".into(), "2250".into()));
     }
 
     #[test]
@@ -0,0 +1,172 @@
+//! Correlates scan lifecycle events with the deaths they caused.
+//!
+//! `TraceScan::submitDeaths` calls into `GhostCore`, so a single transaction
+//! emits `DeathsProcessed` (seen by the [`DeathHandler`]) *before*
+//! `DeathsSubmitted` (seen by the [`ScanHandler`]), and only the latter
+//! carries the scan ID. The [`ScanCorrelator`] is shared by both handlers and
+//! keys everything by `(tx_hash, level)`:
+//!
+//! ```text
+//! DeathHandler  ── attach(tx, level, ids) ──▶ scan known?  stamp scan id
+//!                                                  │ no
+//!                                                  ▼
+//!                                             defer ids
+//! ScanHandler   ── submitted(tx, level, scan) ──▶ deferred ids ──▶ link_deaths_to_scan
+//! ```
+//!
+//! Entries for a scan are dropped when it finalizes.
+//!
+//! [`DeathHandler`]: super::DeathHandler
+//! [`ScanHandler`]: super::ScanHandler
+
+use std::collections::HashMap;
+
+use alloy::primitives::B256;
+use parking_lot::Mutex;
+use uuid::Uuid;
+
+use crate::types::enums::Level;
+
+/// Key of a `submitDeaths` call: the transaction and the scanned level.
+type BatchKey = (B256, Level);
+
+/// A scan a death batch belongs to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ScanRef {
+    /// On-chain scan ID.
+    scan_id: String,
+    /// Database ID of the scan.
+    id: Uuid,
+}
+
+/// In-memory state shared by the scan and death handlers.
+#[derive(Debug, Default)]
+pub struct ScanCorrelator {
+    inner: Mutex<Inner>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    /// Batches whose `DeathsSubmitted` event has been seen.
+    submitted: HashMap<BatchKey, ScanRef>,
+    /// Deaths recorded before their batch's `DeathsSubmitted` event.
+    deferred: HashMap<BatchKey, Vec<Uuid>>,
+}
+
+impl ScanCorrelator {
+    /// Create an empty correlator.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Attach newly recorded deaths to their batch.
+    ///
+    /// Returns the database ID of the scan if the batch's `DeathsSubmitted`
+    /// event has already been seen; otherwise the death IDs are held until
+    /// [`Self::submitted`] is called for the batch.
+    pub fn attach(&self, tx_hash: B256, level: Level, death_ids: &[Uuid]) -> Option<Uuid> {
+        let mut inner = self.inner.lock();
+        let key = (tx_hash, level);
+        if let Some(scan) = inner.submitted.get(&key) {
+            return Some(scan.id);
+        }
+        inner
+            .deferred
+            .entry(key)
+            .or_default()
+            .extend_from_slice(death_ids);
+        None
+    }
+
+    /// Record that a batch belongs to a scan.
+    ///
+    /// Returns the IDs of deaths recorded for the batch before this call,
+    /// which still need to be linked to the scan.
+    pub fn submitted(&self, tx_hash: B256, level: Level, scan_id: &str, id: Uuid) -> Vec<Uuid> {
+        let mut inner = self.inner.lock();
+        let key = (tx_hash, level);
+        inner.submitted.insert(
+            key,
+            ScanRef {
+                scan_id: scan_id.to_string(),
+                id,
+            },
+        );
+        inner.deferred.remove(&key).unwrap_or_default()
+    }
+
+    /// Forget every batch of a finalized scan.
+    pub fn finalized(&self, scan_id: &str) {
+        self.inner
+            .lock()
+            .submitted
+            .retain(|_, scan| scan.scan_id != scan_id);
+    }
+
+    /// Number of deaths waiting for their `DeathsSubmitted` event.
+    #[must_use]
+    pub fn deferred_count(&self) -> usize {
+        self.inner.lock().deferred.values().map(Vec::len).sum()
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deaths_before_submission_are_deferred() {
+        let correlator = ScanCorrelator::new();
+        let tx = B256::repeat_byte(1);
+        let ids = [Uuid::new_v4(), Uuid::new_v4()];
+
+        assert_eq!(correlator.attach(tx, Level::Subnet, &ids), None);
+        assert_eq!(correlator.deferred_count(), 2);
+
+        let scan = Uuid::new_v4();
+        assert_eq!(correlator.submitted(tx, Level::Subnet, "7", scan), ids);
+        assert_eq!(correlator.deferred_count(), 0);
+    }
+
+    #[test]
+    fn deaths_after_submission_get_scan_id() {
+        let correlator = ScanCorrelator::new();
+        let tx = B256::repeat_byte(1);
+        let scan = Uuid::new_v4();
+
+        assert!(
+            correlator
+                .submitted(tx, Level::Subnet, "7", scan)
+                .is_empty()
+        );
+        assert_eq!(
+            correlator.attach(tx, Level::Subnet, &[Uuid::new_v4()]),
+            Some(scan)
+        );
+        // Another level in the same transaction is a different batch
+        assert_eq!(
+            correlator.attach(tx, Level::Darknet, &[Uuid::new_v4()]),
+            None
+        );
+    }
+
+    #[test]
+    fn finalization_forgets_batches() {
+        let correlator = ScanCorrelator::new();
+        let tx = B256::repeat_byte(1);
+        correlator.submitted(tx, Level::Subnet, "7", Uuid::new_v4());
+
+        correlator.finalized("7");
+
+        assert_eq!(
+            correlator.attach(tx, Level::Subnet, &[Uuid::new_v4()]),
+            None
+        );
+    }
+}
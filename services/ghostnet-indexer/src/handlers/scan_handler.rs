@@ -15,12 +15,22 @@
 //! └─────────────────┘     └──────────────────┘     └─────────────────┘
 //! ```
 //!
+//! Events may arrive out of order (e.g. after a gap in indexing). A
+//! `DeathsSubmitted` or `ScanFinalized` for an unknown scan saves a
+//! placeholder scan (see [`Scan::is_placeholder`]) that a later
+//! `ScanExecuted` fills in. Finalization latency is only computed once the
+//! real execution time is known.
+//!
 //! # Architecture
 //!
 //! The handler follows hexagonal architecture principles:
 //! - Receives decoded events from the `EventRouter`
 //! - Uses `ScanStore` port for persistence
 //! - Uses `Cache` port for cache invalidation
+//! - Uses `StatsSink` port (optional) for per-level death counts
+//! - Uses [`ScanCorrelator`] (optional) to link deaths to their scan
+//! - Uses [`ScanPredictor`] (optional), whose level prediction a new scan
+//!   invalidates
 //! - Uses `EventPublisher` port for streaming events
 
 use std::sync::Arc;
@@ -32,12 +42,13 @@ use uuid::Uuid;
 
 use crate::abi::trace_scan;
 use crate::error::Result;
-use crate::handlers::ScanPort;
-use crate::ports::{Cache, ScanStore};
-use crate::types::entities::{Scan, ScanFinalizationData};
+use crate::handlers::{ScanCorrelator, ScanPort};
+use crate::indexer::ScanPredictor;
+use crate::ports::{Cache, ScanStore, StatsSink};
+use crate::types::entities::{LevelStatsDelta, Scan, ScanFinalizationData};
 use crate::types::enums::Level;
 use crate::types::events::EventMetadata;
-use crate::types::primitives::TokenAmount;
+use crate::types::primitives::{BlockNumber, TokenAmount};
 
 // ═══════════════════════════════════════════════════════════════════════════════
 // CONSTANTS
@@ -60,6 +71,12 @@ pub struct ScanHandler<S, C> {
     store: Arc<S>,
     /// Cache for invalidation.
     cache: Arc<C>,
+    /// Sink for aggregate level statistics.
+    stats: Option<Arc<dyn StatsSink>>,
+    /// Correlator shared with the death handler.
+    correlator: Option<Arc<ScanCorrelator>>,
+    /// Predictor serving `GET /scans/next`.
+    predictor: Option<Arc<ScanPredictor<S>>>,
 }
 
 impl<S, C> ScanHandler<S, C>
@@ -69,7 +86,84 @@ where
 {
     /// Create a new scan handler.
     pub const fn new(store: Arc<S>, cache: Arc<C>) -> Self {
-        Self { store, cache }
+        Self {
+            store,
+            cache,
+            stats: None,
+            correlator: None,
+            predictor: None,
+        }
+    }
+
+    /// Record per-level death counts through the given sink.
+    ///
+    /// The sink then owns level cache invalidation after finalization.
+    #[must_use]
+    pub fn with_stats(mut self, stats: Arc<dyn StatsSink>) -> Self {
+        self.stats = Some(stats);
+        self
+    }
+
+    /// Link deaths recorded by the death handler to the scan of their batch.
+    ///
+    /// The same correlator must be given to the `DeathHandler`.
+    #[must_use]
+    pub fn with_correlator(mut self, correlator: Arc<ScanCorrelator>) -> Self {
+        self.correlator = Some(correlator);
+        self
+    }
+
+    /// Invalidate the next-scan prediction of a level when it is scanned.
+    #[must_use]
+    pub fn with_predictor(mut self, predictor: Arc<ScanPredictor<S>>) -> Self {
+        self.predictor = Some(predictor);
+        self
+    }
+
+    /// Get a scan by its on-chain ID, saving a placeholder if it is unknown.
+    async fn get_or_create_scan(
+        &self,
+        scan_id: &str,
+        level: Level,
+        seen_at: chrono::DateTime<Utc>,
+    ) -> Result<Scan> {
+        if let Some(scan) = self.store.get_scan_by_id(scan_id).await? {
+            return Ok(scan);
+        }
+
+        warn!(
+            scan_id = %scan_id,
+            "Scan event received before ScanExecuted, creating placeholder"
+        );
+        let scan = Scan::placeholder(scan_id.to_string(), level, seen_at);
+        self.store.save_scan(&scan).await?;
+        Ok(scan)
+    }
+
+    /// Record the deaths of a finalized scan and the scan itself, or
+    /// invalidate the level directly when no stats sink is configured.
+    fn record_deaths(
+        &self,
+        level: Level,
+        scan_id: &str,
+        death_count: u32,
+        finalized: (BlockNumber, chrono::DateTime<Utc>),
+    ) {
+        let (block, finalized_at) = finalized;
+        match &self.stats {
+            Some(stats) => {
+                stats.record_level(
+                    level,
+                    LevelStatsDelta {
+                        deaths_delta: Some(death_count),
+                        ..LevelStatsDelta::default()
+                    },
+                    finalized_at,
+                );
+                stats.record_scan(level, scan_id, death_count, block, finalized_at);
+            }
+            None => self.cache.invalidate_level(&level),
+        }
     }
 
     /// Convert a u8 level to our Level enum.
@@ -114,7 +208,10 @@ where
         let executed_at = Self::to_datetime(event.executedAt);
 
         // Check if scan already exists (idempotency)
-        if let Some(existing) = self.store.get_scan_by_id(&scan_id).await? {
+        let existing = self.store.get_scan_by_id(&scan_id).await?;
+        if let Some(existing) = &existing
+            && !existing.is_placeholder()
+        {
             warn!(
                 existing_id = %existing.id,
                 "Scan already exists, skipping"
@@ -122,21 +219,33 @@ where
             return Ok(());
         }
 
-        // Create new scan record (Phase 1 - not yet finalized)
-        let scan = Scan {
-            id: Uuid::new_v4(),
-            scan_id: scan_id.clone(),
-            level,
-            seed,
-            executed_at,
-            finalized_at: None,
-            death_count: None,
-            total_dead: None,
-            burned: None,
-            distributed_same_level: None,
-            distributed_upstream: None,
-            protocol_fee: None,
-            survivor_count: None,
+        let scan = if let Some(mut placeholder) = existing {
+            // A later lifecycle event arrived first; keep what it recorded
+            placeholder.level = level;
+            placeholder.seed = seed;
+            placeholder.executed_at = executed_at;
+            placeholder.finalization_latency_ms = placeholder.compute_finalization_latency_ms();
+            debug!(scan_uuid = %placeholder.id, "Filling in placeholder scan");
+            placeholder
+        } else {
+            // Create new scan record (Phase 1 - not yet finalized)
+            Scan {
+                id: Uuid::new_v4(),
+                scan_id: scan_id.clone(),
+                level,
+                seed,
+                executed_at,
+                finalized_at: None,
+                death_count: None,
+                total_dead: None,
+                burned: None,
+                distributed_same_level: None,
+                distributed_upstream: None,
+                protocol_fee: None,
+                survivor_count: None,
+                finalized_block: None,
+                finalization_latency_ms: None,
+            }
         };
 
         // Save to database
@@ -144,6 +253,9 @@ where
 
         // Invalidate cache for this level
         self.cache.invalidate_level(&level);
+        if let Some(predictor) = &self.predictor {
+            predictor.invalidate(level);
+        }
 
         info!(
             scan_uuid = %scan.id,
@@ -158,8 +270,9 @@ where
     /// Handle deaths submission batch.
     ///
     /// Keepers submit death lists in batches to avoid gas limits.
-    /// This event is informational - actual death processing happens
-    /// in the `DeathHandler` when `DeathsProcessed` is emitted.
+    /// Actual death processing happens in the `DeathHandler` when
+    /// `DeathsProcessed` is emitted earlier in the same transaction; deaths
+    /// it recorded for this batch are linked to the scan here.
     #[instrument(skip(self, event, meta), fields(
         level = event.level,
         scan_id = %event.scanId,
@@ -176,8 +289,18 @@ where
         let count = event.count;
         let total_dead = Self::to_token_amount(&event.totalDead);
 
-        // This event is primarily for tracking keeper submissions
-        // The actual death records are created when DeathsProcessed fires
+        let scan = self
+            .get_or_create_scan(&scan_id, level, meta.timestamp)
+            .await?;
+
+        if let Some(correlator) = &self.correlator {
+            let deferred = correlator.submitted(meta.tx_hash, level, &scan_id, scan.id);
+            if !deferred.is_empty() {
+                let linked = self.store.link_deaths_to_scan(&scan_id, &deferred).await?;
+                debug!(scan_id = %scan_id, linked, "Linked deaths to scan");
+            }
+        }
+
         debug!(
             level = ?level,
             scan_id = %scan_id,
@@ -206,6 +329,11 @@ where
         let level = Self::to_level(event.level)?;
         let scan_id = event.scanId.to_string();
         let finalized_at = Self::to_datetime(event.finalizedAt);
+        let finalized_block = BlockNumber::new(meta.block_number);
+
+        if let Some(correlator) = &self.correlator {
+            correlator.finalized(&scan_id);
+        }
 
         // Get existing scan, or create incomplete record if missing
         let Some(existing) = self.store.get_scan_by_id(&scan_id).await? else {
@@ -216,23 +344,22 @@ where
                 "ScanFinalized received but no ScanExecuted found, creating incomplete record"
             );
 
+            // We don't have the seed; use finalized time as executed
             let scan = Scan {
-                id: Uuid::new_v4(),
-                scan_id: scan_id.clone(),
-                level,
-                seed: "unknown".to_string(), // We don't have the seed
-                executed_at: finalized_at,   // Use finalized time as executed
                 finalized_at: Some(finalized_at),
                 death_count: Some(event.deathCount.try_into().unwrap_or(u32::MAX)),
                 total_dead: Some(Self::to_token_amount(&event.totalDead)),
-                burned: None,
-                distributed_same_level: None,
-                distributed_upstream: None,
-                protocol_fee: None,
-                survivor_count: None,
+                finalized_block: Some(finalized_block),
+                ..Scan::placeholder(scan_id.clone(), level, finalized_at)
             };
 
             self.store.save_scan(&scan).await?;
+            self.record_deaths(
+                level,
+                &scan_id,
+                scan.death_count.unwrap_or(0),
+                (finalized_block, finalized_at),
+            );
 
             info!(
                 scan_uuid = %scan.id,
@@ -265,19 +392,27 @@ where
             distributed_upstream: TokenAmount::zero(), // Will be updated by CascadeDistributed
             protocol_fee: TokenAmount::zero(), // Will be updated by CascadeDistributed
             survivor_count: 0,           // Will be updated by SurvivorsUpdated
+            finalized_block,
+            finalization_latency_ms: Scan {
+                finalized_at: Some(finalized_at),
+                ..existing
+            }
+            .compute_finalization_latency_ms(),
         };
 
         // Update scan with finalization data
+        let death_count = finalization.death_count;
+        let latency_ms = finalization.finalization_latency_ms;
         self.store.finalize_scan(&scan_id, finalization).await?;
 
-        // Invalidate cache for this level
-        self.cache.invalidate_level(&level);
+        self.record_deaths(level, &scan_id, death_count, (finalized_block, finalized_at));
 
         info!(
             scan_id = %scan_id,
             level = ?level,
             death_count = %event.deathCount,
             total_dead = %Self::to_token_amount(&event.totalDead),
+            latency_ms = ?latency_ms,
             block = meta.block_number,
             "Scan finalized (Phase 2)"
         );
@@ -291,12 +426,12 @@ where
 // ═══════════════════════════════════════════════════════════════════════════════
 
 #[cfg(test)]
-#[allow(clippy::unwrap_used)]
+#[allow(clippy::unwrap_used, clippy::significant_drop_tightening)]
 mod tests {
     use std::collections::HashMap;
     use std::sync::RwLock;
 
-    use alloy::primitives::U256;
+    use alloy::primitives::{B256, U256};
     use chrono::Utc;
 
     use super::*;
@@ -311,6 +446,7 @@ mod tests {
     #[derive(Debug, Default)]
     struct MockScanStore {
         scans: RwLock<HashMap<String, Scan>>,
+        links: RwLock<Vec<(String, Vec<Uuid>)>>,
     }
 
     impl MockScanStore {
@@ -331,7 +467,9 @@ mod tests {
     impl ScanStore for MockScanStore {
         async fn save_scan(&self, scan: &Scan) -> Result<()> {
             let mut scans = self.scans.write().unwrap();
-            scans.insert(scan.scan_id.clone(), scan.clone());
+            if scans.get(&scan.scan_id).is_none_or(Scan::is_placeholder) {
+                scans.insert(scan.scan_id.clone(), scan.clone());
+            }
             Ok(())
         }
 
@@ -346,6 +484,8 @@ mod tests {
                 scan.distributed_upstream = Some(data.distributed_upstream);
                 scan.protocol_fee = Some(data.protocol_fee);
                 scan.survivor_count = Some(data.survivor_count);
+                scan.finalized_block = Some(data.finalized_block);
+                scan.finalization_latency_ms = data.finalization_latency_ms;
                 Ok(())
             } else {
                 Err(crate::error::InfraError::NotFound.into())
@@ -359,7 +499,7 @@ mod tests {
                 .filter(|s| s.level == level)
                 .cloned()
                 .collect();
-            result.sort_by(|a, b| b.executed_at.cmp(&a.executed_at));
+            result.sort_by_key(|s| std::cmp::Reverse(s.executed_at));
             result.truncate(limit as usize);
             Ok(result)
         }
@@ -376,6 +516,17 @@ mod tests {
                 .cloned()
                 .collect())
         }
+
+        async fn link_deaths_to_scan(&self, scan_id: &str, death_ids: &[Uuid]) -> Result<u64> {
+            if !self.scans.read().unwrap().contains_key(scan_id) {
+                return Err(crate::error::InfraError::NotFound.into());
+            }
+            self.links
+                .write()
+                .unwrap()
+                .push((scan_id.to_string(), death_ids.to_vec()));
+            Ok(death_ids.len() as u64)
+        }
     }
 
     // ═══════════════════════════════════════════════════════════════════════════
@@ -397,6 +548,9 @@ mod tests {
             log_index: 0,
             timestamp: Utc::now(),
             contract: test_address(),
+            chain_id: 6343,
+            tx_function: None,
+            tx_from: None,
         }
     }
 
@@ -411,6 +565,49 @@ mod tests {
         (handler, store, cache)
     }
 
+    fn create_correlated_handler() -> (
+        ScanHandler<MockScanStore, MockCache>,
+        Arc<MockScanStore>,
+        Arc<ScanCorrelator>,
+    ) {
+        let (handler, store, _cache) = create_handler();
+        let correlator = Arc::new(ScanCorrelator::new());
+        (
+            handler.with_correlator(Arc::clone(&correlator)),
+            store,
+            correlator,
+        )
+    }
+
+    fn scan_executed(scan_id: u64, executed_at: u64) -> trace_scan::ScanExecuted {
+        trace_scan::ScanExecuted {
+            level: 3,
+            scanId: U256::from(scan_id),
+            seed: U256::from(123_456_789),
+            executedAt: executed_at,
+        }
+    }
+
+    fn deaths_submitted(scan_id: u64) -> trace_scan::DeathsSubmitted {
+        trace_scan::DeathsSubmitted {
+            level: 3,
+            scanId: U256::from(scan_id),
+            count: U256::from(2),
+            totalDead: U256::from(200_u64) * U256::from(10_u64).pow(U256::from(18_u64)),
+            submitter: test_address(),
+        }
+    }
+
+    fn scan_finalized(scan_id: u64, finalized_at: u64) -> trace_scan::ScanFinalized {
+        trace_scan::ScanFinalized {
+            level: 3,
+            scanId: U256::from(scan_id),
+            deathCount: U256::from(2),
+            totalDead: U256::from(200_u64) * U256::from(10_u64).pow(U256::from(18_u64)),
+            finalizedAt: finalized_at,
+        }
+    }
+
     // ═══════════════════════════════════════════════════════════════════════════
     // TESTS
     // ═══════════════════════════════════════════════════════════════════════════
@@ -470,6 +667,30 @@ mod tests {
         assert_eq!(store.scan_count(), 1);
     }
 
+    #[tokio::test]
+    async fn scan_executed_invalidates_the_prediction() {
+        let (handler, store, _cache) = create_handler();
+        let predictor = Arc::new(ScanPredictor::new(
+            Arc::clone(&store),
+            &crate::config::ScanPredictionSettings::default(),
+        ));
+        let handler = handler.with_predictor(Arc::clone(&predictor));
+
+        handler
+            .handle_scan_executed(scan_executed(1, 1_700_000_000), test_metadata())
+            .await
+            .unwrap();
+        assert_eq!(predictor.predict(Level::Subnet).await.unwrap(), None);
+
+        // Without invalidation the cached "no prediction" would stand
+        handler
+            .handle_scan_executed(scan_executed(2, 1_700_028_800), test_metadata())
+            .await
+            .unwrap();
+        let prediction = predictor.predict(Level::Subnet).await.unwrap().unwrap();
+        assert_eq!(prediction.next_scan_at.timestamp(), 1_700_057_600);
+    }
+
     #[tokio::test]
     async fn handle_deaths_submitted_logs_batch() {
         let (handler, _store, _cache) = create_handler();
@@ -592,6 +813,134 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn lifecycle_in_order_links_deaths_and_computes_latency() {
+        let (handler, store, correlator) = create_correlated_handler();
+        let meta = test_metadata();
+
+        handler
+            .handle_scan_executed(scan_executed(1, 1_700_000_000), meta.clone())
+            .await
+            .unwrap();
+
+        // DeathsProcessed precedes DeathsSubmitted in the submitDeaths transaction
+        let deaths = vec![Uuid::new_v4(), Uuid::new_v4()];
+        assert_eq!(
+            correlator.attach(meta.tx_hash, Level::Subnet, &deaths),
+            None
+        );
+        handler
+            .handle_deaths_submitted(deaths_submitted(1), meta.clone())
+            .await
+            .unwrap();
+
+        assert_eq!(*store.links.read().unwrap(), [("1".to_string(), deaths)]);
+        assert_eq!(correlator.deferred_count(), 0);
+
+        // Later batches of the same transaction get the scan id directly
+        let scan = store.get_scan("1").unwrap();
+        assert_eq!(
+            correlator.attach(meta.tx_hash, Level::Subnet, &[Uuid::new_v4()]),
+            Some(scan.id)
+        );
+
+        handler
+            .handle_scan_finalized(scan_finalized(1, 1_700_000_090), meta)
+            .await
+            .unwrap();
+
+        let scan = store.get_scan("1").unwrap();
+        assert_eq!(scan.finalization_latency_ms, Some(90_000));
+        assert_eq!(scan.finalized_block, Some(BlockNumber::new(1000)));
+    }
+
+    #[tokio::test]
+    async fn deaths_submitted_before_scan_executed_uses_placeholder() {
+        let (handler, store, correlator) = create_correlated_handler();
+        let meta = test_metadata();
+
+        let deaths = vec![Uuid::new_v4()];
+        correlator.attach(meta.tx_hash, Level::Subnet, &deaths);
+        handler
+            .handle_deaths_submitted(deaths_submitted(5), meta.clone())
+            .await
+            .unwrap();
+
+        let placeholder = store.get_scan("5").unwrap();
+        assert!(placeholder.is_placeholder());
+        assert_eq!(*store.links.read().unwrap(), [("5".to_string(), deaths)]);
+
+        handler
+            .handle_scan_executed(scan_executed(5, 1_700_000_000), meta.clone())
+            .await
+            .unwrap();
+
+        let scan = store.get_scan("5").unwrap();
+        assert_eq!(
+            scan.id, placeholder.id,
+            "linked deaths keep pointing at the scan"
+        );
+        assert_eq!(scan.seed, "123456789");
+        assert!(!scan.is_placeholder());
+
+        handler
+            .handle_scan_finalized(scan_finalized(5, 1_700_000_030), meta)
+            .await
+            .unwrap();
+
+        let scan = store.get_scan("5").unwrap();
+        assert_eq!(scan.finalization_latency_ms, Some(30_000));
+    }
+
+    #[tokio::test]
+    async fn finalization_before_execution_fills_latency_late() {
+        let (handler, store, _correlator) = create_correlated_handler();
+
+        handler
+            .handle_scan_finalized(scan_finalized(6, 1_700_000_060), test_metadata())
+            .await
+            .unwrap();
+        assert_eq!(store.get_scan("6").unwrap().finalization_latency_ms, None);
+
+        handler
+            .handle_scan_executed(scan_executed(6, 1_700_000_000), test_metadata())
+            .await
+            .unwrap();
+
+        let scan = store.get_scan("6").unwrap();
+        assert!(scan.is_finalized());
+        assert_eq!(scan.finalization_latency_ms, Some(60_000));
+    }
+
+    #[tokio::test]
+    async fn scan_that_never_finalizes_has_no_latency() {
+        let (handler, store, correlator) = create_correlated_handler();
+        let meta = test_metadata();
+
+        handler
+            .handle_scan_executed(scan_executed(2, 1_700_000_000), meta.clone())
+            .await
+            .unwrap();
+        handler
+            .handle_deaths_submitted(deaths_submitted(2), meta.clone())
+            .await
+            .unwrap();
+
+        let scan = store.get_scan("2").unwrap();
+        assert!(!scan.is_finalized());
+        assert_eq!(scan.finalization_latency_ms, None);
+        // The batch stays correlated until the scan finalizes
+        assert_eq!(
+            correlator.attach(meta.tx_hash, Level::Subnet, &[Uuid::new_v4()]),
+            Some(scan.id)
+        );
+        assert!(
+            correlator
+                .attach(B256::repeat_byte(9), Level::Subnet, &[Uuid::new_v4()])
+                .is_none()
+        );
+    }
+
     #[test]
     fn to_level_valid_values() {
         assert!(ScanHandler::<MockScanStore, MockCache>::to_level(0).is_ok());
@@ -21,28 +21,49 @@
 //! # Design Notes
 //!
 //! Token events are high-volume (every taxed transfer emits 3 events).
-//! This handler currently focuses on logging and cache invalidation.
-//! Detailed persistence (balance tracking, transfer history) can be
-//! added later when the stats infrastructure is more mature.
+//! Every event feeds the token flow aggregates (burns, tax, per-address
+//! inflow/outflow) through the `StatsSink`, which buckets them by hour.
+//! Raw transfers are only persisted when `token_flows.persist_transfers`
+//! is enabled.
+//!
+//! Burns are counted once, from the `Transfer` to the dead address. The tax
+//! burn emits both that transfer and `TaxBurned`, so `TaxBurned` is only
+//! recorded as the tax share of the burn, not added to the burn total.
+//!
+//! # Holder Balances
+//!
+//! With a `HolderStore`, every transfer debits the sender and credits the
+//! recipient in wei. The zero address (mints, `burn`) and the dead address
+//! (tax and protocol burns) hold nothing. A transfer is applied to a holder
+//! only if it comes after the last one applied, so replays leave balances
+//! as they are. A debit larger than the balance is clamped to zero and
+//! flagged, for the `HolderReconciler` to read the balance from the chain.
 //!
 //! # Architecture
 //!
 //! The handler follows hexagonal architecture principles:
 //! - Receives decoded events from the `EventRouter`
 //! - Uses `Cache` port for cache invalidation
+//! - Uses `StatsSink` port (optional) for the global burn total and token flows
+//! - Uses `TokenFlowStore` port (optional) for raw transfer history
+//! - Uses `HolderStore` port (optional) for holder balances
 //! - Logs events for analytics and debugging
 
 use std::sync::Arc;
 
+use alloy::primitives::U256;
 use async_trait::async_trait;
-use tracing::{debug, info, instrument};
+use tracing::{debug, info, instrument, warn};
 
 use crate::abi::data_token;
+use crate::config::TokenFlowSettings;
 use crate::error::Result;
 use crate::handlers::TokenPort;
-use crate::ports::Cache;
+use crate::obs;
+use crate::ports::{Cache, HolderStore, StatsSink, TokenFlowStore};
+use crate::types::entities::{GlobalStatsDelta, HolderBalance, TokenFlowDelta, TokenTransfer};
 use crate::types::events::EventMetadata;
-use crate::types::primitives::{EthAddress, TokenAmount};
+use crate::types::primitives::{BlockNumber, EthAddress, TokenAmount};
 
 // ═══════════════════════════════════════════════════════════════════════════════
 // CONSTANTS
@@ -63,12 +84,17 @@ const DEAD_ADDRESS: &str = "0x000000000000000000000000000000000000dEaD";
 
 /// Handler for token events.
 ///
-/// Processes events from the `DataToken` contract. Currently focuses on
-/// logging for analytics; detailed persistence can be added later.
-#[derive(Debug)]
+/// Processes events from the `DataToken` contract, feeding the token flow
+/// aggregates and (optionally) the raw transfer history.
 pub struct TokenHandler<C> {
     /// Cache for invalidation.
     cache: Arc<C>,
+    /// Sink for aggregate statistics.
+    stats: Option<Arc<dyn StatsSink>>,
+    /// Store for raw transfers, set when transfer persistence is enabled.
+    transfers: Option<Arc<dyn TokenFlowStore>>,
+    /// Store for holder balances.
+    holders: Option<Arc<dyn HolderStore>>,
 }
 
 impl<C> TokenHandler<C>
@@ -77,7 +103,144 @@ where
 {
     /// Create a new token handler.
     pub const fn new(cache: Arc<C>) -> Self {
-        Self { cache }
+        Self {
+            cache,
+            stats: None,
+            transfers: None,
+            holders: None,
+        }
+    }
+
+    /// Record burns and token flows in the aggregate statistics through the
+    /// given sink.
+    #[must_use]
+    pub fn with_stats(mut self, stats: Arc<dyn StatsSink>) -> Self {
+        self.stats = Some(stats);
+        self
+    }
+
+    /// Persist raw transfers to `store` if `settings.persist_transfers` is set.
+    #[must_use]
+    pub fn with_transfer_log(
+        mut self,
+        store: Arc<dyn TokenFlowStore>,
+        settings: &TokenFlowSettings,
+    ) -> Self {
+        self.transfers = settings.persist_transfers.then_some(store);
+        self
+    }
+
+    /// Maintain holder balances in `store`.
+    #[must_use]
+    pub fn with_holder_balances(mut self, store: Arc<dyn HolderStore>) -> Self {
+        self.holders = Some(store);
+        self
+    }
+
+    /// Apply a transfer of `value` wei to the holder balances, if tracked.
+    async fn update_holders(
+        &self,
+        from: EthAddress,
+        to: EthAddress,
+        value: U256,
+        meta: &EventMetadata,
+    ) -> Result<()> {
+        let Some(store) = &self.holders else {
+            return Ok(());
+        };
+
+        // One update for a transfer to self, which would not apply twice
+        if from == to {
+            if Self::holds_balance(&from) {
+                Self::update_holder(store.as_ref(), from, value, value, meta).await?;
+            }
+            return Ok(());
+        }
+        if Self::holds_balance(&from) {
+            Self::update_holder(store.as_ref(), from, value, U256::ZERO, meta).await?;
+        }
+        if Self::holds_balance(&to) {
+            Self::update_holder(store.as_ref(), to, U256::ZERO, value, meta).await?;
+        }
+        Ok(())
+    }
+
+    /// Debit and credit the balance of `address` for the transfer in `meta`.
+    async fn update_holder(
+        store: &dyn HolderStore,
+        address: EthAddress,
+        debit: U256,
+        credit: U256,
+        meta: &EventMetadata,
+    ) -> Result<()> {
+        let block = BlockNumber::new(meta.block_number);
+        let existing = store.get_holder(&address).await?;
+        if existing
+            .as_ref()
+            .is_some_and(|holder| !holder.applies(block, meta.log_index))
+        {
+            debug!(holder = %address, block = meta.block_number, "Transfer already applied");
+            return Ok(());
+        }
+
+        let mut holder = existing.unwrap_or_else(|| HolderBalance::new(address, meta.timestamp));
+        let held = holder.is_holder();
+        if let Some(shortfall) = holder.debit(debit) {
+            warn!(
+                holder = %address,
+                shortfall = %shortfall,
+                block = meta.block_number,
+                "Holder balance underflow, clamped to zero and flagged for reconciliation"
+            );
+        }
+        holder.credit(credit);
+        holder.mark_applied(block, meta.log_index, meta.timestamp);
+        store.save_holder(&holder).await?;
+
+        match (held, holder.is_holder()) {
+            (false, true) => obs::add_token_holder(),
+            (true, false) => obs::remove_token_holder(),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Record a token flow delta, if a stats sink is configured.
+    fn record_flow(&self, delta: TokenFlowDelta, meta: &EventMetadata) {
+        if let Some(stats) = &self.stats {
+            stats.record_token_flow(delta, meta.timestamp);
+        }
+    }
+
+    /// Build the flow delta for a transfer.
+    ///
+    /// The zero address has no outflow (mints) and the dead address has no
+    /// inflow (burns); both are accounted for in the global counters instead.
+    fn transfer_flow(
+        from: EthAddress,
+        to: EthAddress,
+        value: &TokenAmount,
+        transfer_type: TransferType,
+    ) -> TokenFlowDelta {
+        let mut delta = TokenFlowDelta {
+            volume: Some(value.clone()),
+            transfers: 1,
+            ..TokenFlowDelta::default()
+        };
+        if transfer_type == TransferType::Burn {
+            delta.burned = Some(value.clone());
+        }
+        if transfer_type != TransferType::Mint {
+            let sender = delta.addresses.entry(from).or_default();
+            sender.outflow = value.clone();
+            sender.transfers_out = 1;
+        }
+        if transfer_type != TransferType::Burn {
+            let recipient = delta.addresses.entry(to).or_default();
+            recipient.inflow = recipient.inflow.saturating_add(value);
+            recipient.transfers_in += 1;
+        }
+        delta
     }
 
     /// Convert an Alloy Address to our `EthAddress` type.
@@ -100,6 +263,11 @@ where
         address.to_string().eq_ignore_ascii_case(DEAD_ADDRESS)
     }
 
+    /// Check if an address can hold a balance (neither zero nor dead).
+    fn holds_balance(address: &EthAddress) -> bool {
+        !Self::is_zero_address(address) && !Self::is_dead_address(address)
+    }
+
     /// Classify a transfer as mint, burn, or regular transfer.
     fn classify_transfer(from: &EthAddress, to: &EthAddress) -> TransferType {
         if Self::is_zero_address(from) {
@@ -112,6 +280,16 @@ where
     }
 }
 
+impl<C> std::fmt::Debug for TokenHandler<C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TokenHandler")
+            .field("stats", &self.stats)
+            .field("persist_transfers", &self.transfers.is_some())
+            .field("holder_balances", &self.holders.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
 /// Classification of transfer types.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum TransferType {
@@ -142,7 +320,9 @@ where
 {
     /// Handle ERC20 transfer.
     ///
-    /// Logs the transfer with classification (mint/burn/transfer).
+    /// Logs the transfer with classification (mint/burn/transfer), records
+    /// its token flows and, if enabled, persists the raw transfer and updates
+    /// the holder balances.
     /// High-volume event - uses debug level for regular transfers.
     #[instrument(skip(self, event, meta), fields(
         from = %event.from,
@@ -176,6 +356,13 @@ where
                     block = meta.block_number,
                     "Token burn (direct)"
                 );
+
+                if let Some(stats) = &self.stats {
+                    stats.record_global(GlobalStatsDelta {
+                        burned_delta: Some(value.clone()),
+                        ..GlobalStatsDelta::default()
+                    });
+                }
             }
             TransferType::Transfer => {
                 debug!(
@@ -188,8 +375,21 @@ where
             }
         }
 
-        // Invalidate cache since balances changed
-        self.cache.invalidate_all_positions();
+        if let Some(store) = &self.transfers {
+            store
+                .record_transfer(&TokenTransfer {
+                    from_address: from,
+                    to_address: to,
+                    amount: value.clone(),
+                    block_number: BlockNumber::new(meta.block_number),
+                    tx_hash: meta.tx_hash.0,
+                    log_index: meta.log_index,
+                    timestamp: meta.timestamp,
+                })
+                .await?;
+        }
+        self.update_holders(from, to, event.value, &meta).await?;
+        self.record_flow(Self::transfer_flow(from, to, &value, transfer_type), &meta);
 
         Ok(())
     }
@@ -214,8 +414,15 @@ where
             "Tax burned"
         );
 
-        // Note: In the future, we could accumulate this into global stats
-        // self.stats_store.increment_total_burned(&amount).await?;
+        // Not added to the burn totals: the matching Transfer to the dead
+        // address already counts this burn
+        self.record_flow(
+            TokenFlowDelta {
+                tax_burned: Some(amount),
+                ..TokenFlowDelta::default()
+            },
+            &meta,
+        );
 
         Ok(())
     }
@@ -240,8 +447,13 @@ where
             "Tax collected to treasury"
         );
 
-        // Note: In the future, we could track treasury balance
-        // self.stats_store.increment_treasury_balance(&amount).await?;
+        self.record_flow(
+            TokenFlowDelta {
+                tax_collected: Some(amount),
+                ..TokenFlowDelta::default()
+            },
+            &meta,
+        );
 
         Ok(())
     }
@@ -282,11 +494,106 @@ where
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
 mod tests {
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
     use alloy::primitives::U256;
-    use chrono::Utc;
+    use chrono::{DateTime, Utc};
 
     use super::*;
     use crate::ports::MockCache;
+    use crate::types::entities::{AddressFlows, BurnRate, HolderSort};
+
+    /// Flow store that only keeps raw transfers.
+    #[derive(Debug, Default)]
+    struct TransferLog {
+        transfers: Mutex<Vec<TokenTransfer>>,
+    }
+
+    #[async_trait]
+    impl TokenFlowStore for TransferLog {
+        async fn record_transfer(&self, transfer: &TokenTransfer) -> Result<()> {
+            self.transfers.lock().unwrap().push(transfer.clone());
+            Ok(())
+        }
+
+        async fn record_token_flows(&self, _: DateTime<Utc>, _: &TokenFlowDelta) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_burn_rate(&self, window: Duration) -> Result<BurnRate> {
+            let zero = TokenAmount::zero();
+            Ok(BurnRate::new(window, zero.clone(), zero.clone(), zero))
+        }
+
+        async fn get_address_flows(
+            &self,
+            address: &EthAddress,
+            window: Duration,
+        ) -> Result<AddressFlows> {
+            Ok(AddressFlows {
+                address: *address,
+                window_secs: window.as_secs(),
+                inflow: TokenAmount::zero(),
+                outflow: TokenAmount::zero(),
+                transfers_in: 0,
+                transfers_out: 0,
+            })
+        }
+    }
+
+    /// Holder store keeping balances in memory.
+    #[derive(Debug, Default)]
+    struct HolderBook {
+        holders: Mutex<HashMap<EthAddress, HolderBalance>>,
+    }
+
+    impl HolderBook {
+        fn holder(&self, address: alloy::primitives::Address) -> Option<HolderBalance> {
+            self.holders
+                .lock()
+                .unwrap()
+                .get(&EthAddress::from(address))
+                .cloned()
+        }
+    }
+
+    #[async_trait]
+    impl HolderStore for HolderBook {
+        async fn get_holder(&self, address: &EthAddress) -> Result<Option<HolderBalance>> {
+            Ok(self.holders.lock().unwrap().get(address).cloned())
+        }
+
+        async fn save_holder(&self, holder: &HolderBalance) -> Result<()> {
+            self.holders
+                .lock()
+                .unwrap()
+                .insert(holder.address, holder.clone());
+            Ok(())
+        }
+
+        async fn get_top_holders(&self, _: HolderSort, _: u32) -> Result<Vec<HolderBalance>> {
+            Ok(Vec::new())
+        }
+
+        async fn count_holders(&self) -> Result<u64> {
+            Ok(0)
+        }
+
+        async fn get_holders_to_reconcile(&self, _: u32) -> Result<Vec<HolderBalance>> {
+            Ok(Vec::new())
+        }
+
+        async fn reconcile_holder(
+            &self,
+            _: &HolderBalance,
+            _: U256,
+            _: DateTime<Utc>,
+        ) -> Result<bool> {
+            Ok(false)
+        }
+    }
 
     // ═══════════════════════════════════════════════════════════════════════════
     // TEST HELPERS
@@ -325,9 +632,39 @@ mod tests {
             log_index: 0,
             timestamp: Utc::now(),
             contract: test_address(),
+            chain_id: 6343,
+            tx_function: None,
+            tx_from: None,
+        }
+    }
+
+    fn metadata_at(block_number: u64, log_index: u64) -> EventMetadata {
+        EventMetadata {
+            block_number,
+            log_index,
+            ..test_metadata()
         }
     }
 
+    fn transfer(
+        from: alloy::primitives::Address,
+        to: alloy::primitives::Address,
+        value: u64,
+    ) -> data_token::Transfer {
+        data_token::Transfer {
+            from,
+            to,
+            value: U256::from(value),
+        }
+    }
+
+    fn create_holder_handler() -> (TokenHandler<MockCache>, Arc<HolderBook>) {
+        let book = Arc::new(HolderBook::default());
+        let handler =
+            TokenHandler::new(Arc::new(MockCache::new())).with_holder_balances(book.clone());
+        (handler, book)
+    }
+
     fn create_handler() -> (TokenHandler<MockCache>, Arc<MockCache>) {
         let cache = Arc::new(MockCache::new());
         let handler = TokenHandler::new(Arc::clone(&cache));
@@ -442,6 +779,162 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn transfer_log_respects_persist_flag() {
+        let event = || data_token::Transfer {
+            from: test_address(),
+            to: test_address_2(),
+            value: U256::from(5_u64),
+        };
+
+        for persist_transfers in [false, true] {
+            let log = Arc::new(TransferLog::default());
+            let settings = TokenFlowSettings {
+                persist_transfers,
+                ..TokenFlowSettings::default()
+            };
+            let handler = TokenHandler::new(Arc::new(MockCache::new()))
+                .with_transfer_log(log.clone(), &settings);
+
+            handler
+                .handle_transfer(event(), test_metadata())
+                .await
+                .unwrap();
+
+            let transfers = log.transfers.lock().unwrap();
+            assert_eq!(transfers.len(), usize::from(persist_transfers));
+            if let Some(transfer) = transfers.first() {
+                assert_eq!(transfer.from_address, EthAddress::from(test_address()));
+                assert_eq!(transfer.block_number, BlockNumber::new(1000));
+                assert_eq!(transfer.tx_hash, [2u8; 32]);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn holder_balances_follow_mints_transfers_and_burns() {
+        let (handler, book) = create_holder_handler();
+        let (alice, bob) = (test_address(), test_address_2());
+
+        for (i, event) in [
+            transfer(zero_address(), alice, 100),
+            transfer(alice, bob, 30),
+            transfer(alice, dead_address(), 10),
+            transfer(bob, zero_address(), 5),
+            transfer(bob, bob, 25),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            handler
+                .handle_transfer(event, metadata_at(1000, i as u64))
+                .await
+                .unwrap();
+        }
+
+        let alice = book.holder(alice).unwrap();
+        assert_eq!(alice.balance, U256::from(60));
+        assert!(!alice.needs_reconciliation);
+        let bob = book.holder(bob).unwrap();
+        assert_eq!(bob.balance, U256::from(25));
+        assert_eq!(
+            (bob.last_block, bob.last_log_index),
+            (BlockNumber::new(1000), 4)
+        );
+        assert!(book.holder(zero_address()).is_none());
+        assert!(book.holder(dead_address()).is_none());
+    }
+
+    #[tokio::test]
+    async fn replayed_transfers_are_applied_once() {
+        let (handler, book) = create_holder_handler();
+        let mint = || transfer(zero_address(), test_address(), 100);
+
+        handler
+            .handle_transfer(mint(), metadata_at(1000, 0))
+            .await
+            .unwrap();
+        handler
+            .handle_transfer(mint(), metadata_at(1000, 0))
+            .await
+            .unwrap();
+        handler
+            .handle_transfer(mint(), metadata_at(999, 3))
+            .await
+            .unwrap();
+        assert_eq!(
+            book.holder(test_address()).unwrap().balance,
+            U256::from(100)
+        );
+
+        handler
+            .handle_transfer(mint(), metadata_at(1000, 1))
+            .await
+            .unwrap();
+        assert_eq!(
+            book.holder(test_address()).unwrap().balance,
+            U256::from(200)
+        );
+    }
+
+    #[tokio::test]
+    async fn underflow_clamps_and_flags_the_sender() {
+        let (handler, book) = create_holder_handler();
+        handler
+            .handle_transfer(
+                transfer(zero_address(), test_address(), 10),
+                metadata_at(1000, 0),
+            )
+            .await
+            .unwrap();
+
+        // A missed incoming transfer leaves the sender short
+        handler
+            .handle_transfer(
+                transfer(test_address(), test_address_2(), 25),
+                metadata_at(1001, 0),
+            )
+            .await
+            .unwrap();
+
+        let sender = book.holder(test_address()).unwrap();
+        assert_eq!(sender.balance, U256::ZERO);
+        assert!(sender.needs_reconciliation);
+        let recipient = book.holder(test_address_2()).unwrap();
+        assert_eq!(recipient.balance, U256::from(25));
+        assert!(!recipient.needs_reconciliation);
+    }
+
+    #[test]
+    fn transfer_flow_skips_mint_and_burn_sides() {
+        let zero = EthAddress::from_hex(ZERO_ADDRESS).unwrap();
+        let dead = EthAddress::from_hex(DEAD_ADDRESS).unwrap();
+        let holder = EthAddress::from(test_address());
+        let value = TokenAmount::parse("3").unwrap();
+
+        let mint =
+            TokenHandler::<MockCache>::transfer_flow(zero, holder, &value, TransferType::Mint);
+        assert_eq!(mint.addresses.len(), 1);
+        assert_eq!(mint.addresses[&holder].inflow, value);
+        assert!(mint.burned.is_none());
+
+        let burn =
+            TokenHandler::<MockCache>::transfer_flow(holder, dead, &value, TransferType::Burn);
+        assert_eq!(burn.addresses.len(), 1);
+        assert_eq!(burn.addresses[&holder].outflow, value);
+        assert_eq!(burn.burned, Some(value.clone()));
+
+        let to_self = TokenHandler::<MockCache>::transfer_flow(
+            holder,
+            holder,
+            &value,
+            TransferType::Transfer,
+        );
+        let flow = &to_self.addresses[&holder];
+        assert_eq!((flow.transfers_in, flow.transfers_out), (1, 1));
+        assert_eq!(flow.inflow, flow.outflow);
+    }
+
     #[test]
     fn classify_transfer_identifies_mint() {
         let from = EthAddress::from_hex(ZERO_ADDRESS).unwrap();
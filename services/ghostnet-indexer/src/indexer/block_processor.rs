@@ -38,20 +38,22 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use alloy::eips::BlockNumberOrTag;
-use alloy::primitives::Address;
 use alloy::providers::Provider;
 use alloy::rpc::types::{Filter, Log};
 use chrono::{DateTime, Utc};
 use futures::future::join_all;
 use tokio::sync::mpsc;
 use tokio::time::sleep;
+use tokio_util::sync::CancellationToken;
 use tracing::{debug, error, info, instrument, warn};
 
 use megaeth_rpc::MegaEthClient;
-use crate::config::ContractAddresses;
 use crate::error::{InfraError, Result};
+use crate::obs::{self, LogSource};
 use crate::types::events::EventMetadata;
 
+use super::{ContractRegistry, ContractScope, Ingest, SharedRegistry, TxContextResolver};
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // CONSTANTS
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -87,12 +89,20 @@ pub struct BlockProcessor<P> {
     provider: Arc<P>,
     /// Optional MegaETH-specific client for cursor-based pagination.
     megaeth_client: Option<Arc<MegaEthClient>>,
-    /// Parsed contract addresses to monitor.
-    contract_addresses: Vec<Address>,
-    /// Channel for sending logs to the event router.
-    log_sender: mpsc::Sender<(Log, EventMetadata)>,
+    /// Chain indexed, stamped on the metadata of every event.
+    chain_id: u64,
+    /// Contracts to monitor, re-read for every block range.
+    contracts: SharedRegistry,
+    /// Which of the monitored contracts this processor indexes.
+    scope: ContractScope,
+    /// Channel for sending logs to the pipeline.
+    log_sender: mpsc::Sender<Ingest>,
     /// Polling interval for HTTP mode.
     poll_interval: Duration,
+    /// Stops polling and backfill between batches when cancelled.
+    shutdown: CancellationToken,
+    /// Resolves the transaction context of events, when enabled.
+    tx_context: Option<Arc<TxContextResolver>>,
 }
 
 impl<P> BlockProcessor<P>
@@ -104,30 +114,55 @@ where
     /// # Arguments
     ///
     /// * `provider` - RPC provider for blockchain access
-    /// * `contracts` - Contract addresses to monitor
+    /// * `contracts` - Contracts to monitor, or a [`SharedRegistry`] to pick
+    ///   up replacements from
     /// * `log_sender` - Channel for dispatching logs
     /// * `poll_interval` - Interval between polls (for HTTP mode)
-    ///
-    /// # Errors
-    ///
-    /// Returns an error if contract addresses cannot be parsed.
+    #[must_use]
     pub fn new(
         provider: Arc<P>,
-        contracts: &ContractAddresses,
-        log_sender: mpsc::Sender<(Log, EventMetadata)>,
+        contracts: impl Into<SharedRegistry>,
+        log_sender: mpsc::Sender<Ingest>,
         poll_interval: Option<Duration>,
-    ) -> Result<Self> {
-        let contract_addresses = contracts
-            .parse_all()
-            .map_err(|e| InfraError::AddressParsing(format!("Invalid contract address: {e}")))?;
-
-        Ok(Self {
+    ) -> Self {
+        Self {
             provider,
             megaeth_client: None,
-            contract_addresses,
+            chain_id: 0,
+            contracts: contracts.into(),
+            scope: ContractScope::All,
             log_sender,
             poll_interval: poll_interval.unwrap_or(DEFAULT_POLL_INTERVAL),
-        })
+            shutdown: CancellationToken::new(),
+            tx_context: None,
+        }
+    }
+
+    /// Stop pulling new blocks once `shutdown` is cancelled.
+    ///
+    /// The batch in flight when shutdown is requested is completed, so the
+    /// pipeline only ever sees whole block ranges.
+    #[must_use]
+    pub fn with_shutdown(mut self, shutdown: CancellationToken) -> Self {
+        self.shutdown = shutdown;
+        self
+    }
+
+    /// Stamp events with `chain_id`, the chain the provider serves.
+    #[must_use]
+    pub const fn with_chain_id(mut self, chain_id: u64) -> Self {
+        self.chain_id = chain_id;
+        self
+    }
+
+    /// Index only the contracts in `scope`.
+    ///
+    /// Used to backfill a lagging contract on its own, with only its address
+    /// in the log filter, while another processor indexes the rest.
+    #[must_use]
+    pub fn with_scope(mut self, scope: ContractScope) -> Self {
+        self.scope = scope;
+        self
     }
 
     /// Add a MegaETH-specific RPC client for cursor-based pagination.
@@ -145,6 +180,15 @@ where
         self
     }
 
+    /// Attach transaction context to the metadata of every event.
+    ///
+    /// Costs one transaction lookup per uncached transaction.
+    #[must_use]
+    pub fn with_tx_context(mut self, resolver: Arc<TxContextResolver>) -> Self {
+        self.tx_context = Some(resolver);
+        self
+    }
+
     /// Check if cursor-based pagination is available.
     ///
     /// Returns `true` if a MegaETH client is configured and the endpoint
@@ -161,7 +205,7 @@ where
     ///
     /// This method polls the RPC endpoint at regular intervals, fetching logs
     /// for all monitored contracts. It tracks the last processed block and
-    /// continues from there on each iteration.
+    /// continues from there on each iteration, until shutdown is requested.
     ///
     /// # Arguments
     ///
@@ -176,7 +220,7 @@ where
 
         let mut last_processed_block = start_block.saturating_sub(1);
 
-        loop {
+        while !self.shutdown.is_cancelled() {
             // Get the latest block number
             let latest_block = self
                 .provider
@@ -184,6 +228,12 @@ where
                 .await
                 .map_err(|e| InfraError::Rpc(Box::new(e)))?;
 
+            // A single lagging contract would skew the lag of the indexer as a whole
+            if !matches!(self.scope, ContractScope::Only(_)) {
+                let lag = latest_block.saturating_sub(last_processed_block);
+                obs::set_lag_blocks(self.chain_id, lag);
+            }
+
             // Process any new blocks
             if latest_block > last_processed_block {
                 let from_block = last_processed_block + 1;
@@ -213,8 +263,14 @@ where
                 debug!(latest_block, "No new blocks, waiting");
             }
 
-            sleep(self.poll_interval).await;
+            tokio::select! {
+                () = sleep(self.poll_interval) => {}
+                () = self.shutdown.cancelled() => {}
+            }
         }
+
+        info!(last_processed_block, "Block polling stopped");
+        Ok(())
     }
 
     /// Backfill historical blocks from `from_block` to `to_block`.
@@ -242,6 +298,11 @@ where
         let mut current = from_block;
 
         while current <= to_block {
+            if self.shutdown.is_cancelled() {
+                info!(next_block = current, "Backfill stopped by shutdown");
+                return Ok(());
+            }
+
             let batch_end = (current + BACKFILL_BATCH_SIZE - 1).min(to_block);
 
             let log_count = self.process_block_range(current, batch_end).await?;
@@ -289,7 +350,7 @@ where
     /// use ghostnet_indexer::indexer::{BlockProcessor, MegaEthClient};
     ///
     /// let megaeth = MegaEthClient::new("https://6343.rpc.thirdweb.com")?;
-    /// let processor = BlockProcessor::new(provider, contracts, tx)?
+    /// let processor = BlockProcessor::new(provider, contracts, tx, None)
     ///     .with_megaeth_client(Arc::new(megaeth));
     ///
     /// // Use cursor-based pagination for efficient backfill
@@ -300,17 +361,22 @@ where
         let client = self.megaeth_client.as_ref().ok_or_else(|| {
             InfraError::Internal("MegaETH client not configured for cursor backfill".into())
         })?;
+        let contracts = self.contracts.current();
 
         info!(
             from_block,
             to_block,
-            contracts = self.contract_addresses.len(),
+            contracts = contracts
+                .contracts()
+                .filter(|c| self.scope.includes(*c))
+                .count(),
             "Starting cursor-based backfill"
         );
 
         // Fetch logs using cursor pagination
+        let addresses = contracts.addresses_in(&self.scope);
         let (logs, stats) = client
-            .get_logs_with_cursor(from_block, to_block, Some(self.contract_addresses.clone()))
+            .get_logs_with_cursor(from_block, to_block, Some(addresses))
             .await?;
 
         info!(
@@ -324,17 +390,19 @@ where
         let mut sorted_logs = logs;
         sorted_logs.sort_by_key(|log| (log.block_number, log.log_index));
 
-        // Dispatch each log
+        // Dispatch each log (the cursor API filters by address only)
         let mut dispatched = 0usize;
-        for log in sorted_logs {
+        for log in sorted_logs.into_iter().filter(|log| contracts.observe(log)) {
             self.dispatch_log(log).await?;
             dispatched += 1;
 
-            if dispatched % 10_000 == 0 {
+            if dispatched.is_multiple_of(10_000) {
                 debug!(dispatched, "Dispatched logs");
             }
         }
 
+        self.complete_range(to_block).await?;
+
         info!(
             dispatched,
             from_block,
@@ -377,14 +445,21 @@ where
     ///
     /// Returns the number of logs processed.
     async fn process_block_range(&self, from_block: u64, to_block: u64) -> Result<usize> {
+        // The whole range uses the registry it started with
+        let contracts = self.contracts.current();
+
         // Fetch logs for all contracts concurrently
-        let logs = self.fetch_logs_concurrent(from_block, to_block).await?;
+        let logs = self
+            .fetch_logs_concurrent(&contracts, from_block, to_block)
+            .await?;
         let log_count = logs.len();
+        obs::record_logs_received(LogSource::Http, log_count);
 
-        // Process each log
-        for log in logs {
+        // Process each log, skipping any the router can't decode
+        for log in logs.into_iter().filter(|log| contracts.observe(log)) {
             self.dispatch_log(log).await?;
         }
+        self.complete_range(to_block).await?;
 
         Ok(log_count)
     }
@@ -393,17 +468,17 @@ where
     ///
     /// This pattern provides significant performance gains by parallelizing
     /// RPC calls across contracts.
-    async fn fetch_logs_concurrent(&self, from_block: u64, to_block: u64) -> Result<Vec<Log>> {
-        // Build filters for each contract
-        let filters: Vec<Filter> = self
-            .contract_addresses
-            .iter()
-            .map(|contract| {
-                Filter::new()
-                    .address(*contract)
-                    .from_block(from_block)
-                    .to_block(to_block)
-            })
+    async fn fetch_logs_concurrent(
+        &self,
+        contracts: &ContractRegistry,
+        from_block: u64,
+        to_block: u64,
+    ) -> Result<Vec<Log>> {
+        // Build filters for each contract in scope
+        let filters: Vec<Filter> = contracts
+            .contract_filters_in(&self.scope)
+            .into_iter()
+            .map(|filter| filter.from_block(from_block).to_block(to_block))
             .collect();
 
         // Create futures for fetching logs
@@ -437,17 +512,36 @@ where
         let meta = self.build_metadata(&log).await?;
 
         // Send to the event router
-        self.log_sender
-            .send((log, meta))
+        self.send(Ingest::Log(log, meta)).await
+    }
+
+    /// Tell the pipeline that every log up to `to_block` has been sent.
+    async fn complete_range(&self, to_block: u64) -> Result<()> {
+        let block = self
+            .provider
+            .get_block_by_number(BlockNumberOrTag::Number(to_block))
             .await
-            .map_err(|e| InfraError::Internal(format!("Log channel closed: {e}")))?;
+            .map_err(|e| InfraError::Rpc(Box::new(e)))?
+            .ok_or_else(|| InfraError::EventDecoding(format!("Block not found: {to_block}")))?;
 
-        Ok(())
+        self.send(Ingest::BlocksComplete {
+            through: to_block,
+            hash: block.header.hash,
+        })
+        .await
+    }
+
+    async fn send(&self, item: Ingest) -> Result<()> {
+        self.log_sender
+            .send(item)
+            .await
+            .map_err(|e| InfraError::Internal(format!("Log channel closed: {e}")).into())
     }
 
     /// Build event metadata from a log.
     ///
-    /// Fetches the block to get the timestamp.
+    /// Fetches the block to get the timestamp, and the transaction if
+    /// transaction context is enabled.
     async fn build_metadata(&self, log: &Log) -> Result<EventMetadata> {
         let block_number = log
             .block_number
@@ -480,7 +574,7 @@ where
                 InfraError::EventDecoding(format!("Invalid timestamp: {}", block.header.timestamp))
             })?;
 
-        Ok(EventMetadata {
+        let mut meta = EventMetadata {
             block_number,
             block_hash,
             tx_hash,
@@ -488,14 +582,22 @@ where
             log_index,
             timestamp,
             contract: log.address(),
-        })
+            chain_id: self.chain_id,
+            tx_function: None,
+            tx_from: None,
+        };
+        if let Some(resolver) = &self.tx_context {
+            resolver.enrich(&mut meta).await;
+        }
+        Ok(meta)
     }
 
     /// Build a filter covering all indexed contracts for a block range.
     #[allow(dead_code)]
     fn build_filter(&self, from_block: u64, to_block: u64) -> Filter {
-        Filter::new()
-            .address(self.contract_addresses.clone())
+        self.contracts
+            .current()
+            .log_filter()
             .from_block(from_block)
             .to_block(to_block)
     }
@@ -506,10 +608,22 @@ where
 // ═══════════════════════════════════════════════════════════════════════════════
 
 #[cfg(test)]
+#[allow(clippy::unwrap_used)]
 mod tests {
+    use alloy::consensus::transaction::Recovered;
+    use alloy::consensus::{Signed, TxEnvelope, TxLegacy};
+    use alloy::primitives::{Address, B256, Signature};
+    use alloy::providers::ProviderBuilder;
+    use alloy::rpc::types::{Block, Transaction};
+    use alloy::sol_types::SolCall;
+    use alloy::transports::mock::Asserter;
+
     use super::*;
+    use crate::abi::ghost_core;
+    use crate::config::ContractAddresses;
 
     #[test]
+    #[allow(clippy::assertions_on_constants)]
     fn backfill_batch_size_is_reasonable() {
         // Ensure batch size is within reasonable bounds
         assert!(BACKFILL_BATCH_SIZE >= 10);
@@ -521,4 +635,84 @@ mod tests {
         assert!(DEFAULT_POLL_INTERVAL >= Duration::from_millis(100));
         assert!(DEFAULT_POLL_INTERVAL <= Duration::from_secs(60));
     }
+
+    fn processor(asserter: &Asserter) -> BlockProcessor<impl Provider + Clone + 'static> {
+        let provider = ProviderBuilder::new().connect_mocked_client(asserter.clone());
+        let addresses = ContractAddresses {
+            ghost_core: "0x0000000000000000000000000000000000000001".into(),
+            trace_scan: "0x0000000000000000000000000000000000000002".into(),
+            dead_pool: "0x0000000000000000000000000000000000000003".into(),
+            data_token: "0x0000000000000000000000000000000000000004".into(),
+            fee_router: "0x0000000000000000000000000000000000000005".into(),
+            rewards_distributor: "0x0000000000000000000000000000000000000006".into(),
+            disabled: vec![],
+            code_hashes: std::collections::HashMap::new(),
+            additional: std::collections::HashMap::new(),
+        };
+        let contracts = Arc::new(ContractRegistry::from_config(&addresses).unwrap());
+        let (tx, _rx) = mpsc::channel(1);
+        BlockProcessor::new(Arc::new(provider), contracts, tx, None)
+    }
+
+    fn mined_log() -> Log {
+        Log {
+            block_number: Some(7),
+            block_hash: Some(B256::repeat_byte(7)),
+            transaction_hash: Some(B256::repeat_byte(1)),
+            transaction_index: Some(0),
+            log_index: Some(0),
+            ..Log::default()
+        }
+    }
+
+    fn block() -> Block {
+        let mut block = Block::<Transaction>::default();
+        block.header.inner.timestamp = 1_700_000_000;
+        block
+    }
+
+    fn jack_in_tx(from: Address) -> Transaction {
+        let input = ghost_core::jackInCall {
+            amount: alloy::primitives::U256::from(1),
+            level: 1,
+        }
+        .abi_encode();
+        let tx = TxLegacy {
+            input: input.into(),
+            ..TxLegacy::default()
+        };
+        let signed = Signed::new_unchecked(tx, Signature::test_signature(), B256::ZERO);
+        Transaction {
+            inner: Recovered::new_unchecked(TxEnvelope::Legacy(signed), from),
+            block_hash: None,
+            block_number: None,
+            transaction_index: None,
+            effective_gas_price: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn metadata_has_tx_context_only_when_enabled() {
+        let asserter = Asserter::new();
+
+        // Disabled: only the block is fetched
+        asserter.push_success(&block());
+        let meta = processor(&asserter).build_metadata(&mined_log()).await.unwrap();
+        assert_eq!(meta.block_number, 7);
+        assert_eq!((meta.tx_function, meta.tx_from), (None, None));
+
+        // Enabled: the transaction is fetched too
+        let from = Address::repeat_byte(0x42);
+        let tx_provider = ProviderBuilder::new().connect_mocked_client(asserter.clone());
+        let resolver = Arc::new(TxContextResolver::new(tx_provider, 10));
+        asserter.push_success(&block());
+        asserter.push_success(&jack_in_tx(from));
+        let meta = processor(&asserter)
+            .with_tx_context(resolver)
+            .build_metadata(&mined_log())
+            .await
+            .unwrap();
+        assert_eq!(meta.tx_function.as_deref(), Some("GhostCore.jackIn"));
+        assert_eq!(meta.tx_from, Some(from));
+    }
 }
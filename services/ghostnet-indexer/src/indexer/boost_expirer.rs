@@ -0,0 +1,724 @@
+//! Derived boost expiry events.
+//!
+//! Nothing happens on-chain when a boost lapses, and a boost whose position
+//! closes stops applying without an event of its own. The [`BoostExpirer`]
+//! checks every `poll_interval` for boosts that did either, records them as
+//! expired and publishes a `BoostExpired` [`DerivedEvent`] for each to the
+//! `derived` topic. A lapsed yield multiplier no longer weights the stake of
+//! its position, so the expirer weighs the effective stake of those
+//! positions again first, recording the change in level stats if given a
+//! [`StatsSink`].
+//!
+//! ```text
+//! ticker ──▶ BoostExpirer ──▶ BoostStore::get_lapsed_boosts
+//!                 │
+//!                 ├──▶ publish_acknowledged("derived", message ID per boost)
+//!                 ├──▶ BoostStore::reweigh_positions ──▶ StatsSink (optional)
+//!                 └──▶ BoostStore::expire_boosts
+//! ```
+//!
+//! The events are published in the [wire schemas](crate::streaming::wire)
+//! of the `derived` topic.
+//!
+//! # Delivery
+//!
+//! Each boost gets at most one `BoostExpired`. A boost is only recorded as
+//! expired once its event was published, so a failed publish is retried by
+//! the next check, and the event's message ID is derived from the boost, so
+//! an event published again because recording failed (or the indexer
+//! restarted in between) is dropped as a redelivery. Recorded boosts are not
+//! checked again, so positions are weighed before their boosts are recorded:
+//! weighing a position again once its stake is up to date changes nothing.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, instrument, warn};
+
+use crate::config::BoostSettings;
+use crate::error::Result;
+use crate::ports::{BoostStore, Clock, EventPublisher, StatsSink, SystemClock};
+use crate::streaming::{Topic, WireSchemas};
+use crate::types::entities::LapsedBoost;
+use crate::types::events::{BoostExpiredEvent, DerivedEvent};
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// BOOST EXPIRER
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Records lapsed boosts as expired and publishes their `BoostExpired`.
+///
+/// Run one expirer per indexer.
+#[derive(Debug)]
+pub struct BoostExpirer<S, P, K = SystemClock> {
+    /// Store the boosts are read from and recorded in.
+    store: Arc<S>,
+    /// Publisher the derived events are sent through.
+    publisher: Arc<P>,
+    /// Time source the expiries are compared with.
+    clock: Arc<K>,
+    /// Interval between checks.
+    poll_interval: Duration,
+    /// Most boosts expired per check.
+    max_boosts: u32,
+    /// Schema versions the events are published in.
+    schemas: Arc<WireSchemas>,
+    /// Sink for the level stats of reweighed positions.
+    stats: Option<Arc<dyn StatsSink>>,
+}
+
+impl<S: BoostStore, P: EventPublisher> BoostExpirer<S, P> {
+    /// Create a new expirer.
+    #[must_use]
+    pub fn new(store: Arc<S>, publisher: Arc<P>, settings: &BoostSettings) -> Self {
+        Self::with_clock(store, publisher, Arc::new(SystemClock), settings)
+    }
+}
+
+impl<S, P, K> BoostExpirer<S, P, K>
+where
+    S: BoostStore,
+    P: EventPublisher,
+    K: Clock,
+{
+    /// Create a new expirer with a custom clock.
+    #[must_use]
+    pub fn with_clock(
+        store: Arc<S>,
+        publisher: Arc<P>,
+        clock: Arc<K>,
+        settings: &BoostSettings,
+    ) -> Self {
+        Self {
+            store,
+            publisher,
+            clock,
+            poll_interval: settings.poll_interval(),
+            max_boosts: settings.max_boosts.max(1),
+            schemas: Arc::default(),
+            stats: None,
+        }
+    }
+
+    /// Publish the schema versions of `schemas` instead of all of them.
+    #[must_use]
+    pub fn with_schemas(mut self, schemas: Arc<WireSchemas>) -> Self {
+        self.schemas = schemas;
+        self
+    }
+
+    /// Record the effective stake changes of reweighed positions through
+    /// the given sink.
+    #[must_use]
+    pub fn with_stats(mut self, stats: Arc<dyn StatsSink>) -> Self {
+        self.stats = Some(stats);
+        self
+    }
+
+    /// Expire the lapsed boosts once, returning the derived events
+    /// published.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the boosts cannot be read, the events cannot be
+    /// published, their positions cannot be reweighed, or the boosts cannot
+    /// be recorded as expired. The boosts are checked again by the next call
+    /// then.
+    #[instrument(skip(self))]
+    pub async fn expire(&self) -> Result<Vec<DerivedEvent>> {
+        let now = self.clock.now();
+        let lapsed = self.store.get_lapsed_boosts(now, self.max_boosts).await?;
+        if lapsed.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let events: Vec<DerivedEvent> = lapsed
+            .iter()
+            .map(|lapsed| Self::expired_event(lapsed, now))
+            .collect();
+        let mut messages = Vec::with_capacity(events.len());
+        for event in &events {
+            let encoded = self.schemas.encode_derived(event)?;
+            messages.extend(
+                encoded
+                    .into_iter()
+                    .map(|message| message.identified(event.message_id())),
+            );
+        }
+        self.publisher
+            .publish_acknowledged(Topic::Derived.as_str(), &messages)
+            .await?;
+
+        // Closed positions are left as they are, so only the multipliers
+        // lapsed on active positions need it
+        let mut positions: Vec<_> = lapsed
+            .iter()
+            .filter(|lapsed| lapsed.boost.boost_type.weights_stake() && !lapsed.cut_short())
+            .map(|lapsed| lapsed.boost.position_id)
+            .collect();
+        positions.sort_unstable();
+        positions.dedup();
+        if !positions.is_empty() {
+            let changes = self.store.reweigh_positions(&positions, now).await?;
+            if let Some(stats) = &self.stats {
+                for change in &changes {
+                    stats.record_level(change.level, change.delta(), now);
+                }
+            }
+            debug!(count = changes.len(), "Reweighed positions");
+        }
+
+        let expired: Vec<_> = lapsed
+            .iter()
+            .map(|lapsed| (lapsed.boost.id, lapsed.ended_at()))
+            .collect();
+        let recorded = self.store.expire_boosts(&expired).await?;
+        debug!(count = events.len(), recorded, "Published boost expiries");
+        Ok(events)
+    }
+
+    /// The `BoostExpired` event of `lapsed`, derived at `now`.
+    fn expired_event(lapsed: &LapsedBoost, now: DateTime<Utc>) -> DerivedEvent {
+        let boost = &lapsed.boost;
+        DerivedEvent::BoostExpired(BoostExpiredEvent {
+            boost_id: boost.id,
+            user: boost.user_address.into(),
+            boost_type: boost.boost_type,
+            value_bps: boost.value_bps.unsigned_abs(),
+            expiry: boost.expiry,
+            ended_at: lapsed.ended_at(),
+            position_closed: lapsed.cut_short(),
+            derived_at: now,
+        })
+    }
+
+    /// Spawn the background expiry task.
+    ///
+    /// Checks immediately, then every `poll_interval` until `shutdown` is
+    /// cancelled.
+    pub fn spawn_expiry_task(self: &Arc<Self>, shutdown: CancellationToken) -> JoinHandle<()>
+    where
+        S: 'static,
+        P: 'static,
+        K: 'static,
+    {
+        let expirer = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(expirer.poll_interval);
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+            loop {
+                tokio::select! {
+                    () = shutdown.cancelled() => break,
+                    _ = ticker.tick() => {}
+                }
+                if let Err(e) = expirer.expire().await {
+                    warn!(error = %e, "Boost expiry failed");
+                }
+            }
+            info!("Boost expirer stopped");
+        })
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::significant_drop_tightening)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use async_trait::async_trait;
+    use chrono::{TimeDelta, TimeZone};
+    use parking_lot::Mutex;
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::error::InfraError;
+    use crate::ports::{FakeClock, IdentifiedMessage};
+    use crate::streaming::Envelope;
+    use crate::streaming::wire::DerivedEventV1;
+    use crate::types::entities::{
+        Boost, EffectiveStakeChange, GlobalStatsDelta, LevelStatsDelta, Position, TokenFlowDelta,
+    };
+    use crate::types::enums::{BoostType, Level};
+    use crate::types::primitives::{BlockNumber, EthAddress, TokenAmount};
+
+    /// Boost store with the positions closed so far.
+    #[derive(Debug, Default)]
+    struct MemoryBoosts {
+        boosts: Mutex<Vec<Boost>>,
+        /// Exit time by position ID.
+        closed: Mutex<HashMap<Uuid, DateTime<Utc>>>,
+        /// Amount and effective stake by position ID, all on `Level::Subnet`.
+        stakes: Mutex<HashMap<Uuid, (TokenAmount, TokenAmount)>>,
+        /// Remaining failures to record boosts as expired.
+        expire_failures: AtomicU32,
+    }
+
+    impl MemoryBoosts {
+        /// Add a death reduction of `user` on `position` expiring at `expiry`.
+        fn add(&self, user: u8, position: Uuid, expiry: DateTime<Utc>) -> Uuid {
+            self.push(user, position, BoostType::DeathReduction, 2500, expiry)
+        }
+
+        /// Add a yield multiplier of `bps` of `user` on `position` expiring
+        /// at `expiry`.
+        fn multiply(&self, user: u8, position: Uuid, bps: i16, expiry: DateTime<Utc>) -> Uuid {
+            self.push(user, position, BoostType::YieldMultiplier, bps, expiry)
+        }
+
+        fn push(
+            &self,
+            user: u8,
+            position: Uuid,
+            boost_type: BoostType,
+            value_bps: i16,
+            expiry: DateTime<Utc>,
+        ) -> Uuid {
+            let mut boosts = self.boosts.lock();
+            let boost = Boost {
+                id: Uuid::new_v4(),
+                position_id: position,
+                user_address: EthAddress::new([user; 20]),
+                boost_type,
+                value_bps,
+                expiry,
+                block_number: BlockNumber::new(100),
+                log_index: boosts.len() as u64,
+                created_at: start(),
+                expired_at: None,
+            };
+            let id = boost.id;
+            boosts.push(boost);
+            id
+        }
+
+        fn close(&self, position: Uuid, at: DateTime<Utc>) {
+            self.closed.lock().insert(position, at);
+        }
+
+        /// Open `position` with a stake of `amount` tokens, not weighed yet.
+        fn stake(&self, position: Uuid, amount: &str) {
+            let amount = TokenAmount::parse(amount).unwrap();
+            let weighed = amount.clone();
+            self.stakes.lock().insert(position, (amount, weighed));
+        }
+
+        fn effective_stake(&self, position: Uuid) -> String {
+            self.stakes.lock()[&position].1.to_string()
+        }
+
+        fn expired_at(&self, id: Uuid) -> Option<DateTime<Utc>> {
+            let boosts = self.boosts.lock();
+            boosts.iter().find(|b| b.id == id).unwrap().expired_at
+        }
+    }
+
+    #[async_trait]
+    impl BoostStore for MemoryBoosts {
+        async fn save_boost(&self, boost: &Boost) -> Result<()> {
+            self.boosts.lock().push(boost.clone());
+            Ok(())
+        }
+
+        async fn get_active_boosts(
+            &self,
+            address: &EthAddress,
+            now: DateTime<Utc>,
+        ) -> Result<Vec<Boost>> {
+            let closed = self.closed.lock();
+            let boosts = self.boosts.lock();
+            Ok(boosts
+                .iter()
+                .filter(|b| b.user_address == *address && b.is_active(now))
+                .filter(|b| !closed.contains_key(&b.position_id))
+                .cloned()
+                .collect())
+        }
+
+        async fn get_expiring_boosts(
+            &self,
+            now: DateTime<Utc>,
+            before: DateTime<Utc>,
+            limit: u32,
+        ) -> Result<Vec<Boost>> {
+            let boosts = self.boosts.lock();
+            Ok(boosts
+                .iter()
+                .filter(|b| b.is_active(now) && b.expiry < before)
+                .take(limit as usize)
+                .cloned()
+                .collect())
+        }
+
+        async fn get_lapsed_boosts(
+            &self,
+            now: DateTime<Utc>,
+            limit: u32,
+        ) -> Result<Vec<LapsedBoost>> {
+            let closed = self.closed.lock();
+            let boosts = self.boosts.lock();
+            let mut lapsed: Vec<_> = boosts
+                .iter()
+                .filter(|b| b.expired_at.is_none())
+                .map(|b| LapsedBoost {
+                    boost: b.clone(),
+                    position_closed_at: closed.get(&b.position_id).copied(),
+                })
+                .filter(|l| l.boost.expiry <= now || l.position_closed_at.is_some())
+                .collect();
+            lapsed.sort_by_key(LapsedBoost::ended_at);
+            lapsed.truncate(limit as usize);
+            Ok(lapsed)
+        }
+
+        async fn expire_boosts(&self, expired: &[(Uuid, DateTime<Utc>)]) -> Result<u64> {
+            if self
+                .expire_failures
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                .is_ok()
+            {
+                return Err(InfraError::Internal("injected failure".into()).into());
+            }
+            let mut boosts = self.boosts.lock();
+            let mut recorded = 0;
+            for (id, at) in expired {
+                if let Some(boost) = boosts
+                    .iter_mut()
+                    .find(|b| b.id == *id && b.expired_at.is_none())
+                {
+                    boost.expired_at = Some(*at);
+                    recorded += 1;
+                }
+            }
+            Ok(recorded)
+        }
+
+        async fn reweigh_positions(
+            &self,
+            positions: &[Uuid],
+            now: DateTime<Utc>,
+        ) -> Result<Vec<EffectiveStakeChange>> {
+            let closed = self.closed.lock();
+            let boosts = self.boosts.lock();
+            let mut stakes = self.stakes.lock();
+            let mut changes = Vec::new();
+            for id in positions.iter().filter(|id| !closed.contains_key(id)) {
+                let Some((amount, effective)) = stakes.get_mut(id) else {
+                    continue;
+                };
+                let weight = boosts
+                    .iter()
+                    .filter(|b| b.position_id == *id && b.is_active(now))
+                    .map(Boost::stake_weight_bps)
+                    .fold(Position::BASE_STAKE_WEIGHT_BPS, u32::saturating_add);
+                let current = amount.percent_of(weight);
+                if current != *effective {
+                    changes.push(EffectiveStakeChange {
+                        position_id: *id,
+                        level: Level::Subnet,
+                        previous: std::mem::replace(effective, current.clone()),
+                        current,
+                    });
+                }
+            }
+            Ok(changes)
+        }
+    }
+
+    /// Stats sink recording the level deltas.
+    #[derive(Debug, Default)]
+    struct RecordingStats {
+        levels: Mutex<Vec<(Level, LevelStatsDelta)>>,
+    }
+
+    impl StatsSink for RecordingStats {
+        fn record_level(&self, level: Level, delta: LevelStatsDelta, _at: DateTime<Utc>) {
+            self.levels.lock().push((level, delta));
+        }
+
+        fn record_global(&self, _delta: GlobalStatsDelta) {}
+
+        fn record_token_flow(&self, _delta: TokenFlowDelta, _at: DateTime<Utc>) {}
+
+        fn record_scan(&self, _: Level, _: &str, _: u32, _: BlockNumber, _: DateTime<Utc>) {}
+    }
+
+    /// Publisher recording delivered messages, optionally failing.
+    #[derive(Debug, Default)]
+    struct RecordingPublisher {
+        /// Delivered messages as (topic, ID, payload), in delivery order.
+        delivered: Mutex<Vec<(String, u128, Vec<u8>)>>,
+        /// Remaining failures.
+        failures: AtomicU32,
+    }
+
+    impl RecordingPublisher {
+        fn events(&self) -> Vec<DerivedEventV1> {
+            let delivered = self.delivered.lock();
+            delivered
+                .iter()
+                .map(|(_, _, payload)| {
+                    serde_json::from_slice::<Envelope<DerivedEventV1>>(payload)
+                        .unwrap()
+                        .event
+                })
+                .collect()
+        }
+
+        fn ids(&self) -> Vec<u128> {
+            self.delivered.lock().iter().map(|(_, id, _)| *id).collect()
+        }
+    }
+
+    #[async_trait]
+    impl EventPublisher for RecordingPublisher {
+        async fn publish(&self, _event: &crate::types::events::GhostnetEvent) -> Result<()> {
+            Ok(())
+        }
+
+        async fn publish_to_topic(&self, _topic: &str, _payload: &[u8]) -> Result<()> {
+            Ok(())
+        }
+
+        async fn publish_batch(
+            &self,
+            _events: &[crate::types::events::GhostnetEvent],
+        ) -> Result<()> {
+            Ok(())
+        }
+
+        async fn publish_acknowledged(
+            &self,
+            topic: &str,
+            messages: &[IdentifiedMessage],
+        ) -> Result<()> {
+            if self
+                .failures
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                .is_ok()
+            {
+                return Err(InfraError::Streaming("injected failure".into()).into());
+            }
+            let mut delivered = self.delivered.lock();
+            delivered.extend(
+                messages
+                    .iter()
+                    .map(|m| (topic.to_string(), m.id, m.payload.to_vec())),
+            );
+            Ok(())
+        }
+
+        async fn flush(&self) -> Result<()> {
+            Ok(())
+        }
+
+        fn is_connected(&self) -> bool {
+            true
+        }
+    }
+
+    type Expirer = BoostExpirer<MemoryBoosts, RecordingPublisher, FakeClock>;
+
+    fn start() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 3, 1, 12, 0, 0).unwrap()
+    }
+
+    fn expirer() -> (
+        Expirer,
+        Arc<MemoryBoosts>,
+        Arc<RecordingPublisher>,
+        Arc<FakeClock>,
+    ) {
+        let store = Arc::new(MemoryBoosts::default());
+        let publisher = Arc::new(RecordingPublisher::default());
+        let clock = Arc::new(FakeClock::new(start()));
+        let expirer = BoostExpirer::with_clock(
+            Arc::clone(&store),
+            Arc::clone(&publisher),
+            Arc::clone(&clock),
+            &BoostSettings::default(),
+        );
+        (expirer, store, publisher, clock)
+    }
+
+    /// Boost IDs of `events`.
+    fn boost_ids(events: &[DerivedEvent]) -> Vec<Uuid> {
+        events
+            .iter()
+            .filter_map(|event| match event {
+                DerivedEvent::BoostExpired(e) => Some(e.boost_id),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    #[allow(clippy::panic)]
+    async fn expires_each_boost_once_its_expiry_passed() {
+        let (expirer, store, publisher, clock) = expirer();
+        let position = Uuid::new_v4();
+        let short = store.add(1, position, start() + TimeDelta::minutes(10));
+        let long = store.add(1, position, start() + TimeDelta::hours(1));
+
+        assert!(expirer.expire().await.unwrap().is_empty());
+
+        clock.advance(TimeDelta::minutes(15));
+        let events = expirer.expire().await.unwrap();
+        assert_eq!(boost_ids(&events), [short]);
+        let [DerivedEvent::BoostExpired(event)] = events.as_slice() else {
+            panic!("expected BoostExpired, got {events:?}");
+        };
+        assert_eq!(event.ended_at, start() + TimeDelta::minutes(10));
+        assert!(!event.position_closed);
+        assert_eq!(event.derived_at, clock.now());
+        assert_eq!(events[0].aggregate(), format!("position:{:#x}", event.user));
+        assert_eq!(store.expired_at(short), Some(event.ended_at));
+
+        // Running again before the next expiry emits nothing
+        assert!(expirer.expire().await.unwrap().is_empty());
+
+        clock.advance(TimeDelta::hours(1));
+        assert_eq!(boost_ids(&expirer.expire().await.unwrap()), [long]);
+        assert!(expirer.expire().await.unwrap().is_empty());
+
+        let delivered = publisher.delivered.lock().clone();
+        assert_eq!(delivered.len(), 2);
+        assert!(delivered.iter().all(|(topic, _, _)| topic == "derived"));
+        assert!(matches!(
+            publisher.events()[1],
+            DerivedEventV1::BoostExpired { position_closed: false, .. }
+        ));
+    }
+
+    #[tokio::test]
+    #[allow(clippy::panic)]
+    async fn closing_the_position_expires_its_boosts() {
+        let (expirer, store, publisher, clock) = expirer();
+        let position = Uuid::new_v4();
+        let boost = store.add(2, position, start() + TimeDelta::hours(1));
+        let other = store.add(3, Uuid::new_v4(), start() + TimeDelta::hours(1));
+        let user = EthAddress::new([2; 20]);
+
+        let exited = start() + TimeDelta::minutes(5);
+        clock.advance(TimeDelta::minutes(5));
+        store.close(position, exited);
+        // No longer active, though not recorded as expired yet
+        let active = store.get_active_boosts(&user, clock.now()).await.unwrap();
+        assert!(active.is_empty());
+
+        clock.advance(TimeDelta::seconds(5));
+        let events = expirer.expire().await.unwrap();
+        let [DerivedEvent::BoostExpired(event)] = events.as_slice() else {
+            panic!("expected BoostExpired, got {events:?}");
+        };
+        assert_eq!(event.boost_id, boost);
+        assert_eq!(event.ended_at, exited);
+        assert_eq!(event.expiry, start() + TimeDelta::hours(1));
+        assert!(event.position_closed);
+        assert_eq!(store.expired_at(boost), Some(exited));
+        assert_eq!(store.expired_at(other), None);
+
+        // Its expiry passing later does not emit it again
+        clock.advance(TimeDelta::hours(2));
+        assert_eq!(boost_ids(&expirer.expire().await.unwrap()), [other]);
+        assert_eq!(publisher.delivered.lock().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn lapsed_multipliers_no_longer_weight_the_stake() {
+        let (expirer, store, _publisher, clock) = expirer();
+        let stats = Arc::new(RecordingStats::default());
+        let expirer = expirer.with_stats(stats.clone());
+        let position = Uuid::new_v4();
+        store.stake(position, "1000");
+        store.multiply(6, position, 2000, start() + TimeDelta::minutes(10));
+        store.multiply(6, position, 3000, start() + TimeDelta::hours(1));
+        store.add(6, position, start() + TimeDelta::minutes(5));
+        let now = clock.now();
+        store.reweigh_positions(&[position], now).await.unwrap();
+        assert_eq!(store.effective_stake(position), "1500");
+
+        // The death reduction lapsing leaves the stake as it is
+        clock.advance(TimeDelta::minutes(7));
+        assert_eq!(expirer.expire().await.unwrap().len(), 1);
+        assert_eq!(store.effective_stake(position), "1500");
+        assert!(stats.levels.lock().is_empty());
+
+        clock.advance(TimeDelta::minutes(7));
+        assert_eq!(expirer.expire().await.unwrap().len(), 1);
+        assert_eq!(store.effective_stake(position), "1300");
+
+        clock.advance(TimeDelta::hours(1));
+        assert_eq!(expirer.expire().await.unwrap().len(), 1);
+        assert_eq!(store.effective_stake(position), "1000");
+
+        let amount = |tokens: &str| TokenAmount::parse(tokens).unwrap();
+        assert_eq!(
+            *stats.levels.lock(),
+            [
+                (
+                    Level::Subnet,
+                    LevelStatsDelta::effective_stake_changed(amount("1500"), amount("1300"))
+                ),
+                (
+                    Level::Subnet,
+                    LevelStatsDelta::effective_stake_changed(amount("1300"), amount("1000"))
+                ),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn failed_publish_leaves_the_boost_to_the_next_run() {
+        let (expirer, store, publisher, clock) = expirer();
+        let boost = store.add(4, Uuid::new_v4(), start() + TimeDelta::minutes(1));
+        clock.advance(TimeDelta::minutes(2));
+        publisher.failures.store(1, Ordering::SeqCst);
+
+        assert!(expirer.expire().await.is_err());
+        assert_eq!(store.expired_at(boost), None);
+
+        assert_eq!(boost_ids(&expirer.expire().await.unwrap()), [boost]);
+        assert!(expirer.expire().await.unwrap().is_empty());
+        assert_eq!(publisher.delivered.lock().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn boost_published_again_after_failing_to_record_keeps_its_id() {
+        let (expirer, store, publisher, clock) = expirer();
+        let boost = store.add(5, Uuid::new_v4(), start() + TimeDelta::minutes(1));
+        clock.advance(TimeDelta::minutes(2));
+        store.expire_failures.store(1, Ordering::SeqCst);
+
+        assert!(expirer.expire().await.is_err());
+        assert_eq!(store.expired_at(boost), None);
+
+        // A restarted expirer publishes it again, as a redelivery
+        let restarted = BoostExpirer::with_clock(
+            Arc::clone(&store),
+            Arc::clone(&publisher),
+            Arc::clone(&clock),
+            &BoostSettings::default(),
+        );
+        clock.advance(TimeDelta::seconds(30));
+        assert_eq!(boost_ids(&restarted.expire().await.unwrap()), [boost]);
+        assert!(restarted.expire().await.unwrap().is_empty());
+
+        let ids = publisher.ids();
+        assert_eq!(ids.len(), 2);
+        assert_eq!(ids[0], ids[1]);
+        assert_eq!(
+            store.expired_at(boost),
+            Some(start() + TimeDelta::minutes(1))
+        );
+    }
+}
@@ -34,14 +34,25 @@
 //! - **Resume**: Continue from the last checkpoint (default)
 //! - **Reindex**: Start from a specific block (for reprocessing)
 //! - **Genesis**: Start from the beginning (for fresh indexing)
+//!
+//! # Contract Cursors
+//!
+//! Each contract also has a cursor, advanced with the checkpoint of the
+//! processor indexing it. A contract enabled after indexing started is
+//! backfilled by its own processor, whose checkpoints only move that
+//! contract's cursor. On startup, a [`ResumePlan`] decides which contracts
+//! resume together and which still need to catch up.
 
 use alloy::primitives::B256;
 use tracing::{debug, info, instrument, warn};
 
 use crate::error::Result;
+use crate::obs;
 use crate::ports::IndexerStateStore;
 use crate::types::primitives::BlockNumber;
 
+use super::Contract;
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // CHECKPOINT STATE
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -108,6 +119,63 @@ pub enum RecoveryMode {
     StartFrom(BlockNumber),
 }
 
+// ═══════════════════════════════════════════════════════════════════════════════
+// RESUME PLAN
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// How the enabled contracts resume indexing, given their cursors.
+///
+/// Contracts at the highest cursor (the head) resume together. Contracts
+/// behind the head by at most the align window are first backfilled up to
+/// it and then join them; contracts further behind catch up on their own.
+/// Contracts without a cursor are left out until backfilled, unless no
+/// contract has been indexed yet.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResumePlan {
+    /// Highest cursor, or `None` if no contract has been indexed yet.
+    pub head: Option<BlockNumber>,
+    /// Contracts resuming together after the head.
+    pub contracts: Vec<Contract>,
+    /// Contracts to backfill up to the head before resuming, with the first
+    /// block they are missing. Also listed in `contracts`.
+    pub align: Vec<(Contract, BlockNumber)>,
+    /// Contracts too far behind the head, with the block to resume them from.
+    pub catch_up: Vec<(Contract, BlockNumber)>,
+    /// Contracts never indexed.
+    pub missing: Vec<Contract>,
+}
+
+impl ResumePlan {
+    /// Plan the resumption of contracts with the given cursors.
+    #[must_use]
+    pub fn new(cursors: &[(Contract, Option<BlockNumber>)], align_window: u64) -> Self {
+        let head = cursors.iter().filter_map(|(_, cursor)| *cursor).max();
+        let Some(head) = head else {
+            return Self {
+                contracts: cursors.iter().map(|(contract, _)| *contract).collect(),
+                ..Self::default()
+            };
+        };
+
+        let mut plan = Self {
+            head: Some(head),
+            ..Self::default()
+        };
+        for &(contract, cursor) in cursors {
+            match cursor {
+                None => plan.missing.push(contract),
+                Some(cursor) if cursor == head => plan.contracts.push(contract),
+                Some(cursor) if head.value() - cursor.value() <= align_window => {
+                    plan.align.push((contract, cursor.next()));
+                    plan.contracts.push(contract);
+                }
+                Some(cursor) => plan.catch_up.push((contract, cursor.next())),
+            }
+        }
+        plan
+    }
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // CHECKPOINT MANAGER
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -119,6 +187,9 @@ pub enum RecoveryMode {
 /// - Updating checkpoints after successful block processing
 /// - Determining the starting block based on recovery mode
 ///
+/// A manager created with [`Self::for_contracts`] only moves the cursors of
+/// its contracts, leaving the checkpoint of the indexer as a whole alone.
+///
 /// # Type Parameters
 ///
 /// * `S` - Store implementation that provides `IndexerStateStore`
@@ -130,6 +201,14 @@ pub struct CheckpointManager<S> {
     recovery_mode: RecoveryMode,
     /// Minimum block to start indexing from (contract deployment block).
     min_block: BlockNumber,
+    /// Contracts whose cursors move with the checkpoint.
+    contracts: Vec<Contract>,
+    /// Contracts counted in the cursor lag, besides `contracts`.
+    enabled: Vec<Contract>,
+    /// Whether only the cursors are checkpointed.
+    cursors_only: bool,
+    /// Chain the cursor lag is reported for.
+    chain_id: u64,
 }
 
 impl<S> CheckpointManager<S>
@@ -146,9 +225,48 @@ where
             store,
             recovery_mode: RecoveryMode::default(),
             min_block: BlockNumber::new(0),
+            contracts: Vec::new(),
+            enabled: Vec::new(),
+            cursors_only: false,
+            chain_id: 0,
         }
     }
 
+    /// Create a manager that checkpoints only the cursors of `contracts`.
+    ///
+    /// Used when backfilling contracts on their own, so the checkpoint of
+    /// the indexer as a whole is left where the other contracts are.
+    pub fn for_contracts(store: S, contracts: impl IntoIterator<Item = Contract>) -> Self {
+        Self {
+            cursors_only: true,
+            ..Self::new(store).with_contracts(contracts)
+        }
+    }
+
+    /// Advance the cursors of `contracts` along with the checkpoint.
+    #[must_use]
+    pub fn with_contracts(mut self, contracts: impl IntoIterator<Item = Contract>) -> Self {
+        self.contracts = dedup(contracts);
+        self
+    }
+
+    /// Report the cursor lag over all of `contracts`, including the ones
+    /// being backfilled elsewhere.
+    ///
+    /// Defaults to the contracts whose cursors move with the checkpoint.
+    #[must_use]
+    pub fn with_enabled(mut self, contracts: impl IntoIterator<Item = Contract>) -> Self {
+        self.enabled = dedup(contracts);
+        self
+    }
+
+    /// Report the cursor lag under `chain_id`.
+    #[must_use]
+    pub const fn with_chain_id(mut self, chain_id: u64) -> Self {
+        self.chain_id = chain_id;
+        self
+    }
+
     /// Set the recovery mode for startup.
     ///
     /// # Arguments
@@ -160,6 +278,23 @@ where
         self
     }
 
+    /// Checkpoint through `store` instead, keeping the configuration.
+    ///
+    /// Lets startup read the checkpoint through one store and the pipeline
+    /// write it through another, such as a batched one.
+    #[must_use]
+    pub fn with_store<T: IndexerStateStore>(self, store: T) -> CheckpointManager<T> {
+        CheckpointManager {
+            store,
+            recovery_mode: self.recovery_mode,
+            min_block: self.min_block,
+            contracts: self.contracts,
+            enabled: self.enabled,
+            cursors_only: self.cursors_only,
+            chain_id: self.chain_id,
+        }
+    }
+
     /// Set the minimum block to start indexing from.
     ///
     /// This is typically the block where contracts were deployed.
@@ -176,6 +311,9 @@ where
 
     /// Load the current checkpoint state from storage.
     ///
+    /// For a manager created with [`Self::for_contracts`], this is the
+    /// lowest cursor of its contracts.
+    ///
     /// # Returns
     ///
     /// The current checkpoint state.
@@ -185,7 +323,15 @@ where
     /// Returns an error if the store fails to retrieve the checkpoint.
     #[instrument(skip(self))]
     pub async fn load(&self) -> Result<CheckpointState> {
-        let last_block = self.store.get_last_block().await?;
+        let last_block = if self.cursors_only {
+            // Resume the contracts from their lowest cursor
+            self.store
+                .min_cursor(&self.contracts)
+                .await?
+                .unwrap_or(BlockNumber::new(0))
+        } else {
+            self.store.get_last_block().await?
+        };
         let last_hash = self.store.get_block_hash(last_block).await?;
 
         let state = CheckpointState::new(last_block, last_hash);
@@ -275,7 +421,7 @@ where
     /// Returns an error if the store fails to save the checkpoint.
     #[instrument(skip(self), fields(block = %block.value()))]
     pub async fn update(&self, block: BlockNumber, hash: B256) -> Result<()> {
-        self.store.set_last_block(block, hash).await?;
+        self.write(block, hash).await?;
         debug!("Checkpoint updated");
         Ok(())
     }
@@ -296,7 +442,59 @@ where
     #[instrument(skip(self), fields(block = %block.value()))]
     pub async fn reset_to(&self, block: BlockNumber, hash: B256) -> Result<()> {
         info!(block = %block.value(), "Resetting checkpoint after reorg");
-        self.store.set_last_block(block, hash).await?;
+        self.write(block, hash).await
+    }
+
+    /// Load the cursors of `contracts`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store fails to retrieve a cursor.
+    pub async fn cursors(
+        &self,
+        contracts: &[Contract],
+    ) -> Result<Vec<(Contract, Option<BlockNumber>)>> {
+        let mut cursors = Vec::with_capacity(contracts.len());
+        for &contract in contracts {
+            cursors.push((contract, self.store.get_cursor(contract).await?));
+        }
+        Ok(cursors)
+    }
+
+    /// Plan how `contracts` resume from their cursors, see [`ResumePlan`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store fails to retrieve a cursor.
+    pub async fn resume_plan(
+        &self,
+        contracts: &[Contract],
+        align_window: u64,
+    ) -> Result<ResumePlan> {
+        let cursors = self.cursors(contracts).await?;
+        Ok(ResumePlan::new(&cursors, align_window))
+    }
+
+    /// Save `block` as the checkpoint and the cursor of the tracked contracts.
+    async fn write(&self, block: BlockNumber, hash: B256) -> Result<()> {
+        if !self.cursors_only {
+            self.store.set_last_block(block, hash).await?;
+        }
+        for &contract in &self.contracts {
+            self.store.set_cursor(contract, block).await?;
+        }
+
+        if !self.cursors_only && !self.contracts.is_empty() {
+            let enabled = if self.enabled.is_empty() {
+                &self.contracts
+            } else {
+                &self.enabled
+            };
+            if let Some(lowest) = self.store.min_cursor(enabled).await? {
+                let lag = block.value().saturating_sub(lowest.value());
+                obs::set_cursor_lag_blocks(self.chain_id, lag);
+            }
+        }
         Ok(())
     }
 
@@ -312,11 +510,23 @@ where
     }
 }
 
+/// Collect `contracts` without duplicates, keeping their order.
+fn dedup(contracts: impl IntoIterator<Item = Contract>) -> Vec<Contract> {
+    let mut unique = Vec::new();
+    for contract in contracts {
+        if !unique.contains(&contract) {
+            unique.push(contract);
+        }
+    }
+    unique
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // TESTS
 // ═══════════════════════════════════════════════════════════════════════════════
 
 #[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::significant_drop_tightening)]
 mod tests {
     use super::*;
     use std::collections::HashMap;
@@ -327,6 +537,7 @@ mod tests {
     struct MockStateStore {
         last_block: Arc<Mutex<Option<(u64, B256)>>>,
         block_hashes: Arc<Mutex<HashMap<u64, B256>>>,
+        cursors: Arc<Mutex<HashMap<Contract, u64>>>,
     }
 
     #[async_trait::async_trait]
@@ -372,6 +583,26 @@ mod tests {
         async fn prune_old_blocks(&self, _keep_blocks: u64) -> Result<u64> {
             Ok(0)
         }
+
+        async fn get_cursor(&self, contract: Contract) -> Result<Option<BlockNumber>> {
+            let cursors = self.cursors.lock().unwrap();
+            Ok(cursors.get(&contract).copied().map(BlockNumber::new))
+        }
+
+        async fn set_cursor(&self, contract: Contract, block: BlockNumber) -> Result<()> {
+            let mut cursors = self.cursors.lock().unwrap();
+            cursors.insert(contract, block.value());
+            Ok(())
+        }
+
+        async fn min_cursor(&self, contracts: &[Contract]) -> Result<Option<BlockNumber>> {
+            let cursors = self.cursors.lock().unwrap();
+            Ok(contracts
+                .iter()
+                .map(|contract| cursors.get(contract).copied().unwrap_or(0))
+                .min()
+                .map(BlockNumber::new))
+        }
     }
 
     impl MockStateStore {
@@ -505,6 +736,92 @@ mod tests {
         assert_eq!(state.last_block.value(), 400);
     }
 
+    #[tokio::test]
+    async fn update_advances_tracked_cursors() {
+        let store = MockStateStore::default();
+        let manager = CheckpointManager::new(store.clone())
+            .with_contracts([Contract::GhostCore, Contract::TraceScan])
+            .with_enabled([Contract::GhostCore, Contract::TraceScan, Contract::DeadPool]);
+
+        manager
+            .update(BlockNumber::new(100), B256::from([0xAA; 32]))
+            .await
+            .unwrap();
+
+        let cursors = manager
+            .cursors(&[Contract::GhostCore, Contract::TraceScan, Contract::DeadPool])
+            .await
+            .unwrap();
+        assert_eq!(
+            cursors,
+            vec![
+                (Contract::GhostCore, Some(BlockNumber::new(100))),
+                (Contract::TraceScan, Some(BlockNumber::new(100))),
+                (Contract::DeadPool, None),
+            ]
+        );
+        assert_eq!(store.get_last_block().await.unwrap().value(), 100);
+    }
+
+    #[tokio::test]
+    async fn contract_checkpoints_leave_global_checkpoint_alone() {
+        let store = MockStateStore::with_checkpoint(500, B256::from([0xBB; 32]));
+        let manager = CheckpointManager::for_contracts(store.clone(), [Contract::DeadPool]);
+
+        manager
+            .update(BlockNumber::new(300), B256::from([0xAA; 32]))
+            .await
+            .unwrap();
+
+        assert_eq!(manager.load().await.unwrap().last_block.value(), 300);
+        assert_eq!(store.get_last_block().await.unwrap().value(), 500);
+        assert_eq!(store.get_cursor(Contract::GhostCore).await.unwrap(), None);
+    }
+
+    #[test]
+    fn resume_plan_splits_contracts_by_cursor() {
+        let at = |block| Some(BlockNumber::new(block));
+        let plan = ResumePlan::new(
+            &[
+                (Contract::GhostCore, at(1_000)),
+                (Contract::TraceScan, at(990)),
+                (Contract::DeadPool, at(100)),
+                (Contract::DataToken, None),
+            ],
+            50,
+        );
+
+        assert_eq!(plan.head, at(1_000));
+        assert_eq!(
+            plan.contracts,
+            vec![Contract::GhostCore, Contract::TraceScan]
+        );
+        assert_eq!(
+            plan.align,
+            vec![(Contract::TraceScan, BlockNumber::new(991))]
+        );
+        assert_eq!(
+            plan.catch_up,
+            vec![(Contract::DeadPool, BlockNumber::new(101))]
+        );
+        assert_eq!(plan.missing, vec![Contract::DataToken]);
+    }
+
+    #[test]
+    fn resume_plan_without_cursors_starts_everything_together() {
+        let plan = ResumePlan::new(
+            &[(Contract::GhostCore, None), (Contract::DeadPool, None)],
+            50,
+        );
+
+        assert_eq!(plan.head, None);
+        assert_eq!(
+            plan.contracts,
+            vec![Contract::GhostCore, Contract::DeadPool]
+        );
+        assert!(plan.missing.is_empty());
+    }
+
     #[test]
     fn manager_is_send_sync() {
         fn assert_send_sync<T: Send + Sync>() {}
@@ -0,0 +1,758 @@
+//! Registry of the GHOSTNET contracts the indexer watches.
+//!
+//! The [`ContractRegistry`] is built from [`ContractAddresses`] and shared
+//! by the [`BlockProcessor`](super::BlockProcessor) and
+//! [`RealtimeProcessor`](super::RealtimeProcessor) through a
+//! [`SharedRegistry`], which a config reload can swap. It is responsible for:
+//! - Resolving which contracts are enabled and where they are deployed
+//! - Building log filters that only match events the router can decode
+//! - Checking at startup that each address holds the expected contract
+//! - Counting logs from our contracts whose topic0 we don't recognise
+//!
+//! # Configuration
+//!
+//! ```toml
+//! [contracts]
+//! ghost_core = "0x..."
+//! # ...
+//! disabled = ["dead_pool"]
+//!
+//! [contracts.additional]
+//! dead_pool = ["0x..."]  # A previous deployment, still indexed
+//!
+//! [contracts.code_hashes]
+//! ghost_core = "0x3f2a91"
+//! ```
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use alloy::primitives::{Address, B256, keccak256};
+use alloy::providers::Provider;
+use alloy::rpc::types::{Filter, Log};
+use tokio::sync::watch;
+use tracing::{info, instrument, warn};
+
+use crate::config::ContractAddresses;
+use crate::error::{AppError, InfraError, Result};
+
+use super::EventKind;
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// CONTRACTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// A GHOSTNET contract emitting indexed events.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Contract {
+    /// Main game logic.
+    GhostCore,
+    /// Scan execution.
+    TraceScan,
+    /// Prediction market.
+    DeadPool,
+    /// $DATA ERC20.
+    DataToken,
+    /// Fee collection.
+    FeeRouter,
+    /// Emissions.
+    RewardsDistributor,
+}
+
+impl Contract {
+    /// All contracts, in configuration order.
+    pub const ALL: [Self; 6] = [
+        Self::GhostCore,
+        Self::TraceScan,
+        Self::DeadPool,
+        Self::DataToken,
+        Self::FeeRouter,
+        Self::RewardsDistributor,
+    ];
+
+    /// Configuration key of the contract.
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::GhostCore => "ghost_core",
+            Self::TraceScan => "trace_scan",
+            Self::DeadPool => "dead_pool",
+            Self::DataToken => "data_token",
+            Self::FeeRouter => "fee_router",
+            Self::RewardsDistributor => "rewards_distributor",
+        }
+    }
+
+    /// Signature hashes (topic0) of the events the router decodes for this
+    /// contract.
+    #[must_use]
+    pub fn topics(self) -> Vec<B256> {
+        EventKind::ALL
+            .into_iter()
+            .filter(|kind| kind.contract() == self)
+            .map(EventKind::signature_hash)
+            .collect()
+    }
+
+    /// Configured address string of the contract.
+    pub(crate) fn configured_address(self, contracts: &ContractAddresses) -> &str {
+        match self {
+            Self::GhostCore => &contracts.ghost_core,
+            Self::TraceScan => &contracts.trace_scan,
+            Self::DeadPool => &contracts.dead_pool,
+            Self::DataToken => &contracts.data_token,
+            Self::FeeRouter => &contracts.fee_router,
+            Self::RewardsDistributor => &contracts.rewards_distributor,
+        }
+    }
+}
+
+impl std::fmt::Display for Contract {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for Contract {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|contract| contract.as_str() == s)
+            .ok_or_else(|| format!("Unknown contract: {s}"))
+    }
+}
+
+/// The enabled contracts a processor indexes.
+///
+/// Lets a lagging contract be backfilled on its own while another processor
+/// keeps indexing the rest.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum ContractScope {
+    /// Every enabled contract.
+    #[default]
+    All,
+    /// A single contract.
+    Only(Contract),
+    /// Every enabled contract except these.
+    Except(Vec<Contract>),
+}
+
+impl ContractScope {
+    /// Whether `contract` is in scope.
+    #[must_use]
+    pub fn includes(&self, contract: Contract) -> bool {
+        match self {
+            Self::All => true,
+            Self::Only(only) => *only == contract,
+            Self::Except(excluded) => !excluded.contains(&contract),
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// VERIFICATION
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Outcome of checking a contract's deployed bytecode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verification {
+    /// The bytecode hash matches the configured prefix.
+    Verified,
+    /// Code is deployed, but no hash prefix is configured to compare against.
+    Deployed,
+    /// The bytecode could not be fetched.
+    Unverified,
+}
+
+/// An enabled contract and its deployment.
+#[derive(Debug, Clone)]
+struct Entry {
+    contract: Contract,
+    address: Address,
+    /// Lowercase hex prefix of the expected bytecode hash, without `0x`.
+    code_hash_prefix: Option<String>,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// CONTRACT REGISTRY
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// The set of contracts the indexer watches.
+///
+/// Disabled contracts are left out of every filter, so their logs are never
+/// fetched. Logs from enabled contracts with an unrecognised topic0 are
+/// counted by [`Self::observe`] and reported through the
+/// `indexer_unknown_topic_logs_total` metric.
+#[derive(Debug)]
+pub struct ContractRegistry {
+    /// Enabled contracts, in configuration order.
+    entries: Vec<Entry>,
+    /// Logs seen from our contracts with an unknown topic0.
+    unknown_topic_logs: AtomicU64,
+}
+
+impl ContractRegistry {
+    /// Build the registry from configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the address or code hash of an enabled contract
+    /// is invalid, or if a contract name is not recognised.
+    pub fn from_config(contracts: &ContractAddresses) -> Result<Self> {
+        let disabled = contracts
+            .disabled
+            .iter()
+            .map(|name| name.parse::<Contract>())
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(AppError::Config)?;
+
+        let mut code_hashes = Vec::with_capacity(contracts.code_hashes.len());
+        for (name, prefix) in &contracts.code_hashes {
+            let contract = name.parse::<Contract>().map_err(AppError::Config)?;
+            let prefix = prefix.trim_start_matches("0x").to_ascii_lowercase();
+            if prefix.is_empty() || !prefix.bytes().all(|b| b.is_ascii_hexdigit()) {
+                return Err(AppError::Config(format!(
+                    "Invalid code hash for {contract}"
+                )));
+            }
+            code_hashes.push((contract, prefix));
+        }
+
+        for name in contracts.additional.keys() {
+            name.parse::<Contract>().map_err(AppError::Config)?;
+        }
+
+        let mut entries = Vec::new();
+        for contract in Contract::ALL.into_iter().filter(|c| !disabled.contains(c)) {
+            let code_hash_prefix = code_hashes
+                .iter()
+                .find(|(c, _)| *c == contract)
+                .map(|(_, prefix)| prefix.clone());
+            entries.push(Entry {
+                contract,
+                address: parse_address(contract, contract.configured_address(contracts))?,
+                code_hash_prefix,
+            });
+
+            // Other deployments may run other bytecode; only their code is checked
+            for raw in contracts.additional.get(contract.as_str()).into_iter().flatten() {
+                entries.push(Entry {
+                    contract,
+                    address: parse_address(contract, raw)?,
+                    code_hash_prefix: None,
+                });
+            }
+        }
+
+        Ok(Self {
+            entries,
+            unknown_topic_logs: AtomicU64::new(0),
+        })
+    }
+
+    /// Enabled contracts, in configuration order, once per deployment.
+    pub fn contracts(&self) -> impl Iterator<Item = Contract> + '_ {
+        self.entries.iter().map(|entry| entry.contract)
+    }
+
+    /// Whether a contract is enabled.
+    #[must_use]
+    pub fn is_enabled(&self, contract: Contract) -> bool {
+        self.entries.iter().any(|entry| entry.contract == contract)
+    }
+
+    /// Main deployment of a contract; `None` if it is disabled.
+    #[must_use]
+    pub fn address(&self, contract: Contract) -> Option<Address> {
+        self.entries
+            .iter()
+            .find(|entry| entry.contract == contract)
+            .map(|entry| entry.address)
+    }
+
+    /// Addresses of the enabled contracts.
+    #[must_use]
+    pub fn addresses(&self) -> Vec<Address> {
+        self.addresses_in(&ContractScope::All)
+    }
+
+    /// Addresses of the enabled contracts in `scope`.
+    #[must_use]
+    pub fn addresses_in(&self, scope: &ContractScope) -> Vec<Address> {
+        self.entries
+            .iter()
+            .filter(|entry| scope.includes(entry.contract))
+            .map(|entry| entry.address)
+            .collect()
+    }
+
+    /// Signature hashes of every event emitted by the enabled contracts.
+    #[must_use]
+    pub fn topics(&self) -> Vec<B256> {
+        self.entries
+            .iter()
+            .flat_map(|entry| entry.contract.topics())
+            .collect()
+    }
+
+    /// Filter matching every event of every enabled contract.
+    ///
+    /// Block bounds are left to the caller.
+    #[must_use]
+    pub fn log_filter(&self) -> Filter {
+        Filter::new()
+            .address(self.addresses())
+            .event_signature(self.topics())
+    }
+
+    /// Per-contract filters, for fetching each contract's logs separately.
+    ///
+    /// Block bounds are left to the caller.
+    #[must_use]
+    pub fn contract_filters(&self) -> Vec<Filter> {
+        self.contract_filters_in(&ContractScope::All)
+    }
+
+    /// Per-contract filters of the enabled contracts in `scope`.
+    ///
+    /// Block bounds are left to the caller.
+    #[must_use]
+    pub fn contract_filters_in(&self, scope: &ContractScope) -> Vec<Filter> {
+        self.entries
+            .iter()
+            .filter(|entry| scope.includes(entry.contract))
+            .map(|entry| {
+                Filter::new()
+                    .address(entry.address)
+                    .event_signature(entry.contract.topics())
+            })
+            .collect()
+    }
+
+    /// Check whether a fetched log should be routed.
+    ///
+    /// Returns `false` for logs from enabled contracts whose topic0 is not a
+    /// known event, after counting them. Logs from other addresses are
+    /// ignored without being counted.
+    pub fn observe(&self, log: &Log) -> bool {
+        let Some(entry) = self
+            .entries
+            .iter()
+            .find(|entry| entry.address == log.address())
+        else {
+            return false;
+        };
+
+        let known = log
+            .topics()
+            .first()
+            .and_then(EventKind::from_topic)
+            .is_some_and(|kind| kind.contract() == entry.contract);
+        if !known {
+            self.unknown_topic_logs.fetch_add(1, Ordering::Relaxed);
+            metrics::counter!(
+                "indexer_unknown_topic_logs_total",
+                "contract" => entry.contract.as_str()
+            )
+            .increment(1);
+            warn!(
+                contract = %entry.contract,
+                topic0 = ?log.topics().first(),
+                block = ?log.block_number,
+                "Log with unknown topic from a GHOSTNET contract"
+            );
+        }
+        known
+    }
+
+    /// Number of logs seen from our contracts with an unknown topic0.
+    #[must_use]
+    pub fn unknown_topic_logs(&self) -> u64 {
+        self.unknown_topic_logs.load(Ordering::Relaxed)
+    }
+
+    /// Check that each enabled contract is deployed where configured.
+    ///
+    /// Contracts with a configured code hash prefix are compared against the
+    /// keccak256 hash of their deployed bytecode. Contracts whose code
+    /// cannot be fetched are logged and reported as
+    /// [`Verification::Unverified`] rather than failing startup.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a contract has no code at its address or its
+    /// bytecode hash does not match the configured prefix.
+    #[instrument(skip_all)]
+    pub async fn verify<P: Provider>(&self, provider: &P) -> Result<Vec<(Contract, Verification)>> {
+        let mut results = Vec::with_capacity(self.entries.len());
+
+        for entry in &self.entries {
+            let code = match provider.get_code_at(entry.address).await {
+                Ok(code) => code,
+                Err(e) => {
+                    warn!(
+                        contract = %entry.contract,
+                        address = %entry.address,
+                        error = %e,
+                        "Could not fetch contract code, skipping verification"
+                    );
+                    results.push((entry.contract, Verification::Unverified));
+                    continue;
+                }
+            };
+
+            if code.is_empty() {
+                return Err(AppError::Config(format!(
+                    "No contract deployed for {} at {}",
+                    entry.contract, entry.address
+                )));
+            }
+
+            let verification = match &entry.code_hash_prefix {
+                Some(prefix) => {
+                    let hash = alloy::hex::encode(keccak256(&code));
+                    if !hash.starts_with(prefix.as_str()) {
+                        return Err(AppError::Config(format!(
+                            "Bytecode of {} at {} does not match: expected 0x{prefix}…, got 0x{hash}",
+                            entry.contract, entry.address
+                        )));
+                    }
+                    Verification::Verified
+                }
+                None => Verification::Deployed,
+            };
+
+            info!(
+                contract = %entry.contract,
+                address = %entry.address,
+                ?verification,
+                "Contract verified"
+            );
+            results.push((entry.contract, verification));
+        }
+
+        Ok(results)
+    }
+}
+
+/// Parse the configured address of a contract.
+fn parse_address(contract: Contract, raw: &str) -> Result<Address> {
+    raw.parse::<Address>().map_err(|e| {
+        InfraError::AddressParsing(format!(
+            "Invalid contract address for {contract} '{raw}': {e}"
+        ))
+        .into()
+    })
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// SHARED REGISTRY
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// A [`ContractRegistry`] that can be replaced while the processors run.
+///
+/// Processors take the [`current`](Self::current) registry once per block
+/// range or subscription, so a batch in flight finishes with the filter it
+/// started with and the next one uses the replacement.
+#[derive(Debug, Clone)]
+pub struct SharedRegistry {
+    sender: Arc<watch::Sender<Arc<ContractRegistry>>>,
+}
+
+impl SharedRegistry {
+    /// Share `registry`.
+    #[must_use]
+    pub fn new(registry: Arc<ContractRegistry>) -> Self {
+        Self {
+            sender: Arc::new(watch::Sender::new(registry)),
+        }
+    }
+
+    /// The registry in use.
+    #[must_use]
+    pub fn current(&self) -> Arc<ContractRegistry> {
+        Arc::clone(&self.sender.borrow())
+    }
+
+    /// Replace the registry.
+    pub fn replace(&self, registry: Arc<ContractRegistry>) {
+        self.sender.send_replace(registry);
+    }
+
+    /// Watch for replacements of the registry.
+    #[must_use]
+    pub fn subscribe(&self) -> watch::Receiver<Arc<ContractRegistry>> {
+        self.sender.subscribe()
+    }
+}
+
+impl From<Arc<ContractRegistry>> for SharedRegistry {
+    fn from(registry: Arc<ContractRegistry>) -> Self {
+        Self::new(registry)
+    }
+}
+
+impl From<ContractRegistry> for SharedRegistry {
+    fn from(registry: ContractRegistry) -> Self {
+        Self::new(Arc::new(registry))
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::collections::HashMap;
+
+    use alloy::primitives::{Bytes, LogData};
+    use alloy::providers::ProviderBuilder;
+    use alloy::rpc::types::FilterSet;
+    use alloy::sol_types::SolEvent;
+    use alloy::transports::mock::Asserter;
+
+    use super::*;
+    use crate::abi::{data_token, dead_pool, ghost_core, rewards_distributor};
+
+    fn addresses() -> ContractAddresses {
+        ContractAddresses {
+            ghost_core: "0x0000000000000000000000000000000000000001".into(),
+            trace_scan: "0x0000000000000000000000000000000000000002".into(),
+            dead_pool: "0x0000000000000000000000000000000000000003".into(),
+            data_token: "0x0000000000000000000000000000000000000004".into(),
+            fee_router: "0x0000000000000000000000000000000000000005".into(),
+            rewards_distributor: "0x0000000000000000000000000000000000000006".into(),
+            disabled: vec![],
+            code_hashes: HashMap::new(),
+            additional: HashMap::new(),
+        }
+    }
+
+    fn log_from(address: Address, topic0: B256) -> Log {
+        Log {
+            inner: alloy::primitives::Log {
+                address,
+                data: LogData::new_unchecked(vec![topic0], Bytes::new()),
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn contract_names_roundtrip() {
+        for contract in Contract::ALL {
+            assert_eq!(contract.as_str().parse::<Contract>(), Ok(contract));
+        }
+        assert!("deadpool".parse::<Contract>().is_err());
+    }
+
+    #[test]
+    fn filter_covers_all_known_events() {
+        let registry = ContractRegistry::from_config(&addresses()).unwrap();
+        let filter = registry.log_filter();
+
+        assert_eq!(filter.address.len(), 6);
+        assert_eq!(filter.topics[0].len(), 27);
+        assert!(filter.topics[0].contains(&ghost_core::JackedIn::SIGNATURE_HASH));
+        assert!(filter.topics[0].contains(&rewards_distributor::TokensClaimed::SIGNATURE_HASH));
+        assert!(filter.topics[1..].iter().all(FilterSet::is_empty));
+    }
+
+    #[test]
+    fn contract_filters_match_one_contract_each() {
+        let registry = ContractRegistry::from_config(&addresses()).unwrap();
+        let filters = registry.contract_filters();
+
+        assert_eq!(filters.len(), 6);
+        let trace_scan = &filters[1];
+        assert_eq!(trace_scan.address.len(), 1);
+        assert!(trace_scan.address.contains(&Address::with_last_byte(2)));
+        assert_eq!(trace_scan.topics[0].len(), 3);
+    }
+
+    #[test]
+    fn scoped_filters_leave_out_other_contracts() {
+        let registry = ContractRegistry::from_config(&addresses()).unwrap();
+
+        let only = registry.contract_filters_in(&ContractScope::Only(Contract::DeadPool));
+        assert_eq!(only.len(), 1);
+        assert!(only[0].address.contains(&Address::with_last_byte(3)));
+        assert_eq!(
+            registry.addresses_in(&ContractScope::Only(Contract::DeadPool)),
+            vec![Address::with_last_byte(3)]
+        );
+
+        let except = ContractScope::Except(vec![Contract::DeadPool, Contract::FeeRouter]);
+        let addresses = registry.addresses_in(&except);
+        assert_eq!(addresses.len(), 4);
+        assert!(!addresses.contains(&Address::with_last_byte(3)));
+        assert_eq!(registry.contract_filters_in(&except).len(), 4);
+    }
+
+    #[test]
+    fn disabled_contract_is_left_out() {
+        let mut config = addresses();
+        config.disabled = vec!["dead_pool".into()];
+        // A disabled contract needs no valid address
+        config.dead_pool = String::new();
+
+        let registry = ContractRegistry::from_config(&config).unwrap();
+        assert!(!registry.is_enabled(Contract::DeadPool));
+        assert_eq!(registry.contracts().count(), 5);
+
+        let filter = registry.log_filter();
+        assert!(!filter.address.contains(&Address::with_last_byte(3)));
+        assert_eq!(filter.topics[0].len(), 23);
+        assert!(!filter.topics[0].contains(&dead_pool::BetPlaced::SIGNATURE_HASH));
+
+        let bet = log_from(
+            Address::with_last_byte(3),
+            dead_pool::BetPlaced::SIGNATURE_HASH,
+        );
+        assert!(!registry.observe(&bet));
+        assert_eq!(registry.unknown_topic_logs(), 0);
+    }
+
+    #[test]
+    fn additional_deployments_are_watched() {
+        let mut config = addresses();
+        let previous = Address::with_last_byte(0x33);
+        config
+            .additional
+            .insert("dead_pool".into(), vec![previous.to_string()]);
+
+        let registry = ContractRegistry::from_config(&config).unwrap();
+        assert_eq!(registry.contract_filters().len(), 7);
+        assert!(registry.addresses().contains(&previous));
+        assert!(registry.observe(&log_from(previous, dead_pool::BetPlaced::SIGNATURE_HASH)));
+        assert_eq!(
+            registry.address(Contract::DeadPool),
+            Some(Address::with_last_byte(0x03))
+        );
+
+        // Disabling the contract disables every deployment
+        config.disabled = vec!["dead_pool".into()];
+        let registry = ContractRegistry::from_config(&config).unwrap();
+        assert!(!registry.addresses().contains(&previous));
+        assert_eq!(registry.address(Contract::DeadPool), None);
+    }
+
+    #[test]
+    fn shared_registry_hands_out_replacements() {
+        let shared = SharedRegistry::from(ContractRegistry::from_config(&addresses()).unwrap());
+        let updates = shared.subscribe();
+        let in_flight = shared.current();
+
+        let mut config = addresses();
+        config.disabled = vec!["dead_pool".into()];
+        shared.replace(Arc::new(ContractRegistry::from_config(&config).unwrap()));
+
+        assert!(updates.has_changed().unwrap());
+        assert!(!shared.current().is_enabled(Contract::DeadPool));
+        // Holders of the previous registry keep using it
+        assert!(in_flight.is_enabled(Contract::DeadPool));
+    }
+
+    #[test]
+    fn invalid_configuration_is_rejected() {
+        let mut config = addresses();
+        config.disabled = vec!["deadpool".into()];
+        assert!(matches!(
+            ContractRegistry::from_config(&config),
+            Err(AppError::Config(_))
+        ));
+
+        let mut config = addresses();
+        config.ghost_core = "0x1234".into();
+        assert!(ContractRegistry::from_config(&config).is_err());
+
+        let mut config = addresses();
+        config
+            .code_hashes
+            .insert("ghost_core".into(), "0xnothex".into());
+        assert!(ContractRegistry::from_config(&config).is_err());
+
+        let mut config = addresses();
+        config.additional.insert("ghost_core".into(), vec!["0x1234".into()]);
+        assert!(ContractRegistry::from_config(&config).is_err());
+        config.additional = HashMap::from([("ghostcore".into(), vec![])]);
+        assert!(ContractRegistry::from_config(&config).is_err());
+    }
+
+    #[test]
+    fn unknown_topics_are_counted() {
+        let registry = ContractRegistry::from_config(&addresses()).unwrap();
+        let ghost_core = Address::with_last_byte(1);
+
+        assert!(registry.observe(&log_from(ghost_core, ghost_core::JackedIn::SIGNATURE_HASH)));
+        // A known event, but emitted by a different contract
+        assert!(!registry.observe(&log_from(ghost_core, data_token::Transfer::SIGNATURE_HASH)));
+        assert!(!registry.observe(&log_from(ghost_core, B256::repeat_byte(0xFF))));
+        // Logs from other addresses are not ours to count
+        assert!(!registry.observe(&log_from(Address::repeat_byte(0xAA), B256::ZERO)));
+
+        assert_eq!(registry.unknown_topic_logs(), 2);
+    }
+
+    #[tokio::test]
+    async fn verify_checks_code_hash_prefixes() {
+        let code = Bytes::from_static(&[0x60, 0x80, 0x60, 0x40]);
+        let prefix = alloy::hex::encode(keccak256(&code))[..6].to_string();
+
+        let mut config = addresses();
+        config.disabled = [
+            "dead_pool",
+            "data_token",
+            "fee_router",
+            "rewards_distributor",
+        ]
+        .map(String::from)
+        .to_vec();
+        config
+            .code_hashes
+            .insert("ghost_core".into(), format!("0x{prefix}"));
+        let registry = ContractRegistry::from_config(&config).unwrap();
+
+        let asserter = Asserter::new();
+        let provider = ProviderBuilder::new().connect_mocked_client(asserter.clone());
+        asserter.push_success(&code);
+        asserter.push_failure_msg("eth_getCode unavailable");
+
+        let results = registry.verify(&provider).await.unwrap();
+        assert_eq!(
+            results,
+            vec![
+                (Contract::GhostCore, Verification::Verified),
+                (Contract::TraceScan, Verification::Unverified),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn verify_rejects_missing_or_mismatched_code() {
+        let mut config = addresses();
+        config.disabled = ["trace_scan", "dead_pool", "data_token", "fee_router"]
+            .map(String::from)
+            .to_vec();
+        config
+            .code_hashes
+            .insert("ghost_core".into(), "0x000000".into());
+        let registry = ContractRegistry::from_config(&config).unwrap();
+
+        let asserter = Asserter::new();
+        let provider = ProviderBuilder::new().connect_mocked_client(asserter.clone());
+        asserter.push_success(&Bytes::from_static(&[0x60, 0x80]));
+        assert!(registry.verify(&provider).await.is_err());
+
+        asserter.push_success(&Bytes::from_static(&[0x60, 0x80, 0x60, 0x40]));
+        asserter.push_success(&Bytes::new());
+        config.code_hashes.clear();
+        let registry = ContractRegistry::from_config(&config).unwrap();
+        let err = registry.verify(&provider).await.unwrap_err();
+        assert!(err.to_string().contains("rewards_distributor"));
+    }
+}
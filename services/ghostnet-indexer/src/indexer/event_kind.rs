@@ -0,0 +1,279 @@
+//! Event kinds and the topic0 lookup table.
+//!
+//! Every GHOSTNET event is identified by the keccak256 hash of its signature,
+//! carried as the first topic of its log. [`EventKind::from_topic`] resolves
+//! that hash through a table built once on first use, so routing a log costs a
+//! single hash lookup regardless of how many events the indexer knows.
+
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use alloy::primitives::B256;
+use alloy::sol_types::SolEvent;
+
+use crate::abi::{data_token, dead_pool, fee_router, ghost_core, rewards_distributor, trace_scan};
+
+use super::Contract;
+
+/// topic0 → event kind, for all 27 events.
+static TOPIC_TABLE: LazyLock<HashMap<B256, EventKind>> = LazyLock::new(|| {
+    EventKind::ALL
+        .into_iter()
+        .map(|kind| (kind.signature_hash(), kind))
+        .collect()
+});
+
+/// A GHOSTNET event the router can decode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EventKind {
+    // GhostCore
+    /// `GhostCore.JackedIn`
+    JackedIn,
+    /// `GhostCore.StakeAdded`
+    StakeAdded,
+    /// `GhostCore.Extracted`
+    Extracted,
+    /// `GhostCore.BoostApplied`
+    BoostApplied,
+    /// `GhostCore.PositionCulled`
+    PositionCulled,
+    /// `GhostCore.DeathsProcessed`
+    DeathsProcessed,
+    /// `GhostCore.SurvivorsUpdated`
+    SurvivorsUpdated,
+    /// `GhostCore.CascadeDistributed`
+    CascadeDistributed,
+    /// `GhostCore.EmissionsAdded`
+    EmissionsAdded,
+    /// `GhostCore.SystemResetTriggered`
+    SystemResetTriggered,
+
+    // TraceScan
+    /// `TraceScan.ScanExecuted`
+    ScanExecuted,
+    /// `TraceScan.DeathsSubmitted`
+    DeathsSubmitted,
+    /// `TraceScan.ScanFinalized`
+    ScanFinalized,
+
+    // DeadPool
+    /// `DeadPool.RoundCreated`
+    RoundCreated,
+    /// `DeadPool.BetPlaced`
+    BetPlaced,
+    /// `DeadPool.RoundResolved`
+    RoundResolved,
+    /// `DeadPool.WinningsClaimed`
+    WinningsClaimed,
+
+    // DataToken
+    /// `DataToken.Transfer`
+    Transfer,
+    /// `DataToken.TaxBurned`
+    TaxBurned,
+    /// `DataToken.TaxCollected`
+    TaxCollected,
+    /// `DataToken.TaxExclusionSet`
+    TaxExclusionSet,
+
+    // FeeRouter
+    /// `FeeRouter.TollCollected`
+    TollCollected,
+    /// `FeeRouter.BuybackExecuted`
+    BuybackExecuted,
+    /// `FeeRouter.OperationsWithdrawn`
+    OperationsWithdrawn,
+
+    // RewardsDistributor
+    /// `RewardsDistributor.EmissionsDistributed`
+    EmissionsDistributed,
+    /// `RewardsDistributor.WeightsUpdated`
+    WeightsUpdated,
+    /// `RewardsDistributor.TokensClaimed`
+    TokensClaimed,
+}
+
+impl EventKind {
+    /// All event kinds, grouped by contract.
+    pub const ALL: [Self; 27] = [
+        Self::JackedIn,
+        Self::StakeAdded,
+        Self::Extracted,
+        Self::BoostApplied,
+        Self::PositionCulled,
+        Self::DeathsProcessed,
+        Self::SurvivorsUpdated,
+        Self::CascadeDistributed,
+        Self::EmissionsAdded,
+        Self::SystemResetTriggered,
+        Self::ScanExecuted,
+        Self::DeathsSubmitted,
+        Self::ScanFinalized,
+        Self::RoundCreated,
+        Self::BetPlaced,
+        Self::RoundResolved,
+        Self::WinningsClaimed,
+        Self::Transfer,
+        Self::TaxBurned,
+        Self::TaxCollected,
+        Self::TaxExclusionSet,
+        Self::TollCollected,
+        Self::BuybackExecuted,
+        Self::OperationsWithdrawn,
+        Self::EmissionsDistributed,
+        Self::WeightsUpdated,
+        Self::TokensClaimed,
+    ];
+
+    /// Look up the event kind for a log's topic0.
+    #[must_use]
+    pub fn from_topic(topic0: &B256) -> Option<Self> {
+        TOPIC_TABLE.get(topic0).copied()
+    }
+
+    /// Keccak256 hash of the event signature (topic0).
+    #[must_use]
+    pub const fn signature_hash(self) -> B256 {
+        match self {
+            Self::JackedIn => ghost_core::JackedIn::SIGNATURE_HASH,
+            Self::StakeAdded => ghost_core::StakeAdded::SIGNATURE_HASH,
+            Self::Extracted => ghost_core::Extracted::SIGNATURE_HASH,
+            Self::BoostApplied => ghost_core::BoostApplied::SIGNATURE_HASH,
+            Self::PositionCulled => ghost_core::PositionCulled::SIGNATURE_HASH,
+            Self::DeathsProcessed => ghost_core::DeathsProcessed::SIGNATURE_HASH,
+            Self::SurvivorsUpdated => ghost_core::SurvivorsUpdated::SIGNATURE_HASH,
+            Self::CascadeDistributed => ghost_core::CascadeDistributed::SIGNATURE_HASH,
+            Self::EmissionsAdded => ghost_core::EmissionsAdded::SIGNATURE_HASH,
+            Self::SystemResetTriggered => ghost_core::SystemResetTriggered::SIGNATURE_HASH,
+            Self::ScanExecuted => trace_scan::ScanExecuted::SIGNATURE_HASH,
+            Self::DeathsSubmitted => trace_scan::DeathsSubmitted::SIGNATURE_HASH,
+            Self::ScanFinalized => trace_scan::ScanFinalized::SIGNATURE_HASH,
+            Self::RoundCreated => dead_pool::RoundCreated::SIGNATURE_HASH,
+            Self::BetPlaced => dead_pool::BetPlaced::SIGNATURE_HASH,
+            Self::RoundResolved => dead_pool::RoundResolved::SIGNATURE_HASH,
+            Self::WinningsClaimed => dead_pool::WinningsClaimed::SIGNATURE_HASH,
+            Self::Transfer => data_token::Transfer::SIGNATURE_HASH,
+            Self::TaxBurned => data_token::TaxBurned::SIGNATURE_HASH,
+            Self::TaxCollected => data_token::TaxCollected::SIGNATURE_HASH,
+            Self::TaxExclusionSet => data_token::TaxExclusionSet::SIGNATURE_HASH,
+            Self::TollCollected => fee_router::TollCollected::SIGNATURE_HASH,
+            Self::BuybackExecuted => fee_router::BuybackExecuted::SIGNATURE_HASH,
+            Self::OperationsWithdrawn => fee_router::OperationsWithdrawn::SIGNATURE_HASH,
+            Self::EmissionsDistributed => rewards_distributor::EmissionsDistributed::SIGNATURE_HASH,
+            Self::WeightsUpdated => rewards_distributor::WeightsUpdated::SIGNATURE_HASH,
+            Self::TokensClaimed => rewards_distributor::TokensClaimed::SIGNATURE_HASH,
+        }
+    }
+
+    /// Contract that emits the event.
+    #[must_use]
+    pub const fn contract(self) -> Contract {
+        match self {
+            Self::JackedIn
+            | Self::StakeAdded
+            | Self::Extracted
+            | Self::BoostApplied
+            | Self::PositionCulled
+            | Self::DeathsProcessed
+            | Self::SurvivorsUpdated
+            | Self::CascadeDistributed
+            | Self::EmissionsAdded
+            | Self::SystemResetTriggered => Contract::GhostCore,
+            Self::ScanExecuted | Self::DeathsSubmitted | Self::ScanFinalized => Contract::TraceScan,
+            Self::RoundCreated | Self::BetPlaced | Self::RoundResolved | Self::WinningsClaimed => {
+                Contract::DeadPool
+            }
+            Self::Transfer | Self::TaxBurned | Self::TaxCollected | Self::TaxExclusionSet => {
+                Contract::DataToken
+            }
+            Self::TollCollected | Self::BuybackExecuted | Self::OperationsWithdrawn => {
+                Contract::FeeRouter
+            }
+            Self::EmissionsDistributed | Self::WeightsUpdated | Self::TokensClaimed => {
+                Contract::RewardsDistributor
+            }
+        }
+    }
+
+    /// Name of the handler port the router dispatches the event to.
+    #[must_use]
+    pub const fn handler(self) -> &'static str {
+        match self {
+            Self::JackedIn
+            | Self::StakeAdded
+            | Self::Extracted
+            | Self::BoostApplied
+            | Self::PositionCulled => "position",
+            Self::DeathsProcessed
+            | Self::SurvivorsUpdated
+            | Self::CascadeDistributed
+            | Self::EmissionsAdded
+            | Self::SystemResetTriggered => "death",
+            Self::ScanExecuted | Self::DeathsSubmitted | Self::ScanFinalized => "scan",
+            Self::RoundCreated | Self::BetPlaced | Self::RoundResolved | Self::WinningsClaimed => {
+                "market"
+            }
+            Self::Transfer | Self::TaxBurned | Self::TaxCollected | Self::TaxExclusionSet => {
+                "token"
+            }
+            Self::TollCollected | Self::BuybackExecuted | Self::OperationsWithdrawn => "fee",
+            Self::EmissionsDistributed | Self::WeightsUpdated | Self::TokensClaimed => "emissions",
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn table_holds_all_signatures_once() {
+        let hashes: HashSet<B256> = EventKind::ALL.iter().map(|k| k.signature_hash()).collect();
+        assert_eq!(hashes.len(), 27, "signature hashes must be unique");
+        assert_eq!(TOPIC_TABLE.len(), 27);
+
+        for kind in EventKind::ALL {
+            assert_eq!(EventKind::from_topic(&kind.signature_hash()), Some(kind));
+        }
+    }
+
+    #[test]
+    fn unknown_topic_is_not_found() {
+        assert_eq!(EventKind::from_topic(&B256::ZERO), None);
+        assert_eq!(EventKind::from_topic(&B256::repeat_byte(0xFF)), None);
+    }
+
+    #[test]
+    fn events_per_contract() {
+        let count = |contract| {
+            EventKind::ALL
+                .iter()
+                .filter(|k| k.contract() == contract)
+                .count()
+        };
+        assert_eq!(count(Contract::GhostCore), 10);
+        assert_eq!(count(Contract::TraceScan), 3);
+        assert_eq!(count(Contract::DeadPool), 4);
+        assert_eq!(count(Contract::DataToken), 4);
+        assert_eq!(count(Contract::FeeRouter), 3);
+        assert_eq!(count(Contract::RewardsDistributor), 3);
+    }
+
+    #[test]
+    fn events_per_handler() {
+        let count = |handler| EventKind::ALL.iter().filter(|k| k.handler() == handler).count();
+        assert_eq!(count("position"), 5);
+        assert_eq!(count("death"), 5);
+        assert_eq!(count("scan"), 3);
+        assert_eq!(count("market"), 4);
+        assert_eq!(count("token"), 4);
+        assert_eq!(count("fee"), 3);
+        assert_eq!(count("emissions"), 3);
+    }
+}
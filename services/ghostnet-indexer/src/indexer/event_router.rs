@@ -12,18 +12,23 @@
 //! - **Flexibility**: Swap implementations at runtime
 //! - **Type Safety**: Compile-time verification of handler implementations
 
-use alloy::primitives::Log as PrimitiveLog;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+
 use alloy::rpc::types::Log;
 use alloy::sol_types::SolEvent;
-use tracing::{debug, instrument, warn};
+use serde::Serialize;
+use tracing::{debug, error, instrument, warn};
 
 use crate::abi::{data_token, dead_pool, fee_router, ghost_core, rewards_distributor, trace_scan};
 use crate::error::{AppError, InfraError, Result};
 use crate::handlers::{
     DeathPort, EmissionsPort, FeePort, MarketPort, PositionPort, ScanPort, TokenPort,
 };
+use crate::obs;
 use crate::types::events::EventMetadata;
 
+use super::{EventKind, Quarantine};
+
 /// Routes decoded events to appropriate handlers.
 ///
 /// Generic over handler traits to enable testing with mock implementations.
@@ -73,6 +78,16 @@ where
     token_handler: T,
     fee_handler: F,
     emissions_handler: E,
+    /// Logs decoded as a known event.
+    decoded: AtomicU64,
+    /// Logs with an unknown topic0.
+    unknown: AtomicU64,
+    /// Logs with a known topic0 that failed to decode.
+    failed_decode: AtomicU64,
+    /// Bit per [`EventKind`] whose decode failure has been logged.
+    decode_warned: AtomicU32,
+    /// Where logs whose handler keeps failing are set aside, if anywhere.
+    quarantine: Option<Quarantine>,
 }
 
 impl<P, S, D, M, T, F, E> std::fmt::Debug for EventRouter<P, S, D, M, T, F, E>
@@ -94,7 +109,9 @@ where
             .field("token_handler", &std::any::type_name::<T>())
             .field("fee_handler", &std::any::type_name::<F>())
             .field("emissions_handler", &std::any::type_name::<E>())
-            .finish()
+            .field("stats", &self.stats())
+            .field("quarantine", &self.quarantine.is_some())
+            .finish_non_exhaustive()
     }
 }
 
@@ -127,13 +144,26 @@ where
             token_handler,
             fee_handler,
             emissions_handler,
+            decoded: AtomicU64::new(0),
+            unknown: AtomicU64::new(0),
+            failed_decode: AtomicU64::new(0),
+            decode_warned: AtomicU32::new(0),
+            quarantine: None,
         }
     }
 
+    /// Quarantine logs whose handler keeps failing instead of returning the
+    /// error, when `quarantine` is set.
+    #[must_use]
+    pub fn with_quarantine(mut self, quarantine: Option<Quarantine>) -> Self {
+        self.quarantine = quarantine;
+        self
+    }
+
     /// Route a single log to its appropriate handler.
     ///
-    /// Decodes the raw log using the event signature (topic0) to determine
-    /// the event type, then dispatches to the appropriate handler.
+    /// Resolves the event type from the signature hash (topic0) with a single
+    /// table lookup, then decodes the log as that type and dispatches it.
     ///
     /// # Arguments
     ///
@@ -143,7 +173,8 @@ where
     /// # Returns
     ///
     /// * `Ok(true)` - Event was recognized and handled
-    /// * `Ok(false)` - Event was not recognized (unknown signature)
+    /// * `Ok(false)` - Event was not recognized (unknown signature), or was
+    ///   quarantined without reaching its handler
     /// * `Err(_)` - Event decoding or handler error
     ///
     /// # Errors
@@ -152,11 +183,15 @@ where
     /// - Event decoding fails (malformed log data)
     /// - The handler returns an error during processing
     ///
+    /// With a [`Quarantine`], only store outages are returned as they are; a
+    /// log whose handler failed is quarantined and
+    /// [`AppError::Quarantined`] returned, as the handler may have written
+    /// part of it.
+    ///
     /// # Cancellation Safety
     ///
     /// This method is cancellation-safe. If cancelled, no handler will have
     /// partially processed the event - handlers are atomic operations.
-    #[allow(clippy::too_many_lines)] // Large match statement is unavoidable for 27 events
     #[instrument(skip(self, log, meta), fields(topic0 = ?log.topics().first()))]
     pub async fn route_log(&self, log: &Log, meta: EventMetadata) -> Result<bool> {
         let Some(topic0) = log.topics().first() else {
@@ -164,214 +199,193 @@ where
             return Ok(false);
         };
 
-        // Match by event signature hash (topic0)
-        // Each match arm decodes the log and dispatches to the appropriate handler
-        match topic0.as_slice() {
+        let Some(kind) = EventKind::from_topic(topic0) else {
+            self.unknown.fetch_add(1, Ordering::Relaxed);
+            warn!(
+                topic0 = ?topic0,
+                contract = ?meta.contract,
+                "Unknown event signature - not a GHOSTNET event"
+            );
+            return Ok(false);
+        };
+
+        if let Some(quarantine) = &self.quarantine {
+            quarantine
+                .isolate(kind, log, meta, |meta| self.dispatch(kind, log, meta))
+                .await
+        } else {
+            self.dispatch(kind, log, meta).await?;
+            Ok(true)
+        }
+    }
+
+    /// Decode `log` as `kind` and pass it to its handler.
+    #[allow(clippy::too_many_lines)] // Large match statement is unavoidable for 27 events
+    async fn dispatch(&self, kind: EventKind, log: &Log, meta: EventMetadata) -> Result<()> {
+        // Each arm decodes the log as its event type and dispatches it
+        match kind {
             // ═══════════════════════════════════════════════════════════════════
             // GHOST CORE EVENTS (10 events → PositionPort, DeathPort)
             // ═══════════════════════════════════════════════════════════════════
-            x if x == ghost_core::JackedIn::SIGNATURE_HASH.as_slice() => {
-                let event = Self::decode_event::<ghost_core::JackedIn>(&log.inner)?;
-                self.position_handler.handle_jacked_in(event, meta).await?;
-                Ok(true)
+            EventKind::JackedIn => {
+                let event = self.decode::<ghost_core::JackedIn>(kind, log)?;
+                Self::handled(kind, self.position_handler.handle_jacked_in(event, meta)).await?;
             }
-            x if x == ghost_core::StakeAdded::SIGNATURE_HASH.as_slice() => {
-                let event = Self::decode_event::<ghost_core::StakeAdded>(&log.inner)?;
-                self.position_handler
-                    .handle_stake_added(event, meta)
-                    .await?;
-                Ok(true)
+            EventKind::StakeAdded => {
+                let event = self.decode::<ghost_core::StakeAdded>(kind, log)?;
+                Self::handled(kind, self.position_handler.handle_stake_added(event, meta)).await?;
             }
-            x if x == ghost_core::Extracted::SIGNATURE_HASH.as_slice() => {
-                let event = Self::decode_event::<ghost_core::Extracted>(&log.inner)?;
-                self.position_handler.handle_extracted(event, meta).await?;
-                Ok(true)
+            EventKind::Extracted => {
+                let event = self.decode::<ghost_core::Extracted>(kind, log)?;
+                Self::handled(kind, self.position_handler.handle_extracted(event, meta)).await?;
             }
-            x if x == ghost_core::BoostApplied::SIGNATURE_HASH.as_slice() => {
-                let event = Self::decode_event::<ghost_core::BoostApplied>(&log.inner)?;
-                self.position_handler
-                    .handle_boost_applied(event, meta)
-                    .await?;
-                Ok(true)
+            EventKind::BoostApplied => {
+                let event = self.decode::<ghost_core::BoostApplied>(kind, log)?;
+                Self::handled(kind, self.position_handler.handle_boost_applied(event, meta)).await?;
             }
-            x if x == ghost_core::PositionCulled::SIGNATURE_HASH.as_slice() => {
-                let event = Self::decode_event::<ghost_core::PositionCulled>(&log.inner)?;
-                self.position_handler
-                    .handle_position_culled(event, meta)
+            EventKind::PositionCulled => {
+                let event = self.decode::<ghost_core::PositionCulled>(kind, log)?;
+                Self::handled(kind, self.position_handler.handle_position_culled(event, meta))
                     .await?;
-                Ok(true)
             }
-            x if x == ghost_core::DeathsProcessed::SIGNATURE_HASH.as_slice() => {
-                let event = Self::decode_event::<ghost_core::DeathsProcessed>(&log.inner)?;
-                self.death_handler
-                    .handle_deaths_processed(event, meta)
-                    .await?;
-                Ok(true)
+            EventKind::DeathsProcessed => {
+                let event = self.decode::<ghost_core::DeathsProcessed>(kind, log)?;
+                Self::handled(kind, self.death_handler.handle_deaths_processed(event, meta)).await?;
             }
-            x if x == ghost_core::SurvivorsUpdated::SIGNATURE_HASH.as_slice() => {
-                let event = Self::decode_event::<ghost_core::SurvivorsUpdated>(&log.inner)?;
-                self.death_handler
-                    .handle_survivors_updated(event, meta)
+            EventKind::SurvivorsUpdated => {
+                let event = self.decode::<ghost_core::SurvivorsUpdated>(kind, log)?;
+                Self::handled(kind, self.death_handler.handle_survivors_updated(event, meta))
                     .await?;
-                Ok(true)
             }
-            x if x == ghost_core::CascadeDistributed::SIGNATURE_HASH.as_slice() => {
-                let event = Self::decode_event::<ghost_core::CascadeDistributed>(&log.inner)?;
-                self.death_handler
-                    .handle_cascade_distributed(event, meta)
+            EventKind::CascadeDistributed => {
+                let event = self.decode::<ghost_core::CascadeDistributed>(kind, log)?;
+                Self::handled(kind, self.death_handler.handle_cascade_distributed(event, meta))
                     .await?;
-                Ok(true)
             }
-            x if x == ghost_core::EmissionsAdded::SIGNATURE_HASH.as_slice() => {
-                let event = Self::decode_event::<ghost_core::EmissionsAdded>(&log.inner)?;
-                self.death_handler
-                    .handle_emissions_added(event, meta)
-                    .await?;
-                Ok(true)
+            EventKind::EmissionsAdded => {
+                let event = self.decode::<ghost_core::EmissionsAdded>(kind, log)?;
+                Self::handled(kind, self.death_handler.handle_emissions_added(event, meta)).await?;
             }
-            x if x == ghost_core::SystemResetTriggered::SIGNATURE_HASH.as_slice() => {
-                let event = Self::decode_event::<ghost_core::SystemResetTriggered>(&log.inner)?;
-                self.death_handler.handle_system_reset(event, meta).await?;
-                Ok(true)
+            EventKind::SystemResetTriggered => {
+                let event = self.decode::<ghost_core::SystemResetTriggered>(kind, log)?;
+                Self::handled(kind, self.death_handler.handle_system_reset(event, meta)).await?;
             }
 
             // ═══════════════════════════════════════════════════════════════════
             // TRACE SCAN EVENTS (3 events → ScanPort)
             // ═══════════════════════════════════════════════════════════════════
-            x if x == trace_scan::ScanExecuted::SIGNATURE_HASH.as_slice() => {
-                let event = Self::decode_event::<trace_scan::ScanExecuted>(&log.inner)?;
-                self.scan_handler.handle_scan_executed(event, meta).await?;
-                Ok(true)
+            EventKind::ScanExecuted => {
+                let event = self.decode::<trace_scan::ScanExecuted>(kind, log)?;
+                Self::handled(kind, self.scan_handler.handle_scan_executed(event, meta)).await?;
             }
-            x if x == trace_scan::DeathsSubmitted::SIGNATURE_HASH.as_slice() => {
-                let event = Self::decode_event::<trace_scan::DeathsSubmitted>(&log.inner)?;
-                self.scan_handler
-                    .handle_deaths_submitted(event, meta)
-                    .await?;
-                Ok(true)
+            EventKind::DeathsSubmitted => {
+                let event = self.decode::<trace_scan::DeathsSubmitted>(kind, log)?;
+                Self::handled(kind, self.scan_handler.handle_deaths_submitted(event, meta)).await?;
             }
-            x if x == trace_scan::ScanFinalized::SIGNATURE_HASH.as_slice() => {
-                let event = Self::decode_event::<trace_scan::ScanFinalized>(&log.inner)?;
-                self.scan_handler.handle_scan_finalized(event, meta).await?;
-                Ok(true)
+            EventKind::ScanFinalized => {
+                let event = self.decode::<trace_scan::ScanFinalized>(kind, log)?;
+                Self::handled(kind, self.scan_handler.handle_scan_finalized(event, meta)).await?;
             }
 
             // ═══════════════════════════════════════════════════════════════════
             // DEAD POOL EVENTS (4 events → MarketPort)
             // ═══════════════════════════════════════════════════════════════════
-            x if x == dead_pool::RoundCreated::SIGNATURE_HASH.as_slice() => {
-                let event = Self::decode_event::<dead_pool::RoundCreated>(&log.inner)?;
-                self.market_handler
-                    .handle_round_created(event, meta)
-                    .await?;
-                Ok(true)
+            EventKind::RoundCreated => {
+                let event = self.decode::<dead_pool::RoundCreated>(kind, log)?;
+                Self::handled(kind, self.market_handler.handle_round_created(event, meta)).await?;
             }
-            x if x == dead_pool::BetPlaced::SIGNATURE_HASH.as_slice() => {
-                let event = Self::decode_event::<dead_pool::BetPlaced>(&log.inner)?;
-                self.market_handler.handle_bet_placed(event, meta).await?;
-                Ok(true)
+            EventKind::BetPlaced => {
+                let event = self.decode::<dead_pool::BetPlaced>(kind, log)?;
+                Self::handled(kind, self.market_handler.handle_bet_placed(event, meta)).await?;
             }
-            x if x == dead_pool::RoundResolved::SIGNATURE_HASH.as_slice() => {
-                let event = Self::decode_event::<dead_pool::RoundResolved>(&log.inner)?;
-                self.market_handler
-                    .handle_round_resolved(event, meta)
-                    .await?;
-                Ok(true)
+            EventKind::RoundResolved => {
+                let event = self.decode::<dead_pool::RoundResolved>(kind, log)?;
+                Self::handled(kind, self.market_handler.handle_round_resolved(event, meta)).await?;
             }
-            x if x == dead_pool::WinningsClaimed::SIGNATURE_HASH.as_slice() => {
-                let event = Self::decode_event::<dead_pool::WinningsClaimed>(&log.inner)?;
-                self.market_handler
-                    .handle_winnings_claimed(event, meta)
+            EventKind::WinningsClaimed => {
+                let event = self.decode::<dead_pool::WinningsClaimed>(kind, log)?;
+                Self::handled(kind, self.market_handler.handle_winnings_claimed(event, meta))
                     .await?;
-                Ok(true)
             }
 
             // ═══════════════════════════════════════════════════════════════════
             // DATA TOKEN EVENTS (4 events → TokenPort)
             // ═══════════════════════════════════════════════════════════════════
-            x if x == data_token::Transfer::SIGNATURE_HASH.as_slice() => {
-                let event = Self::decode_event::<data_token::Transfer>(&log.inner)?;
-                self.token_handler.handle_transfer(event, meta).await?;
-                Ok(true)
+            EventKind::Transfer => {
+                let event = self.decode::<data_token::Transfer>(kind, log)?;
+                Self::handled(kind, self.token_handler.handle_transfer(event, meta)).await?;
             }
-            x if x == data_token::TaxBurned::SIGNATURE_HASH.as_slice() => {
-                let event = Self::decode_event::<data_token::TaxBurned>(&log.inner)?;
-                self.token_handler.handle_tax_burned(event, meta).await?;
-                Ok(true)
+            EventKind::TaxBurned => {
+                let event = self.decode::<data_token::TaxBurned>(kind, log)?;
+                Self::handled(kind, self.token_handler.handle_tax_burned(event, meta)).await?;
             }
-            x if x == data_token::TaxCollected::SIGNATURE_HASH.as_slice() => {
-                let event = Self::decode_event::<data_token::TaxCollected>(&log.inner)?;
-                self.token_handler.handle_tax_collected(event, meta).await?;
-                Ok(true)
+            EventKind::TaxCollected => {
+                let event = self.decode::<data_token::TaxCollected>(kind, log)?;
+                Self::handled(kind, self.token_handler.handle_tax_collected(event, meta)).await?;
             }
-            x if x == data_token::TaxExclusionSet::SIGNATURE_HASH.as_slice() => {
-                let event = Self::decode_event::<data_token::TaxExclusionSet>(&log.inner)?;
-                self.token_handler
-                    .handle_tax_exclusion_set(event, meta)
+            EventKind::TaxExclusionSet => {
+                let event = self.decode::<data_token::TaxExclusionSet>(kind, log)?;
+                Self::handled(kind, self.token_handler.handle_tax_exclusion_set(event, meta))
                     .await?;
-                Ok(true)
             }
 
             // ═══════════════════════════════════════════════════════════════════
             // FEE ROUTER EVENTS (3 events → FeePort)
             // ═══════════════════════════════════════════════════════════════════
-            x if x == fee_router::TollCollected::SIGNATURE_HASH.as_slice() => {
-                let event = Self::decode_event::<fee_router::TollCollected>(&log.inner)?;
-                self.fee_handler.handle_toll_collected(event, meta).await?;
-                Ok(true)
+            EventKind::TollCollected => {
+                let event = self.decode::<fee_router::TollCollected>(kind, log)?;
+                Self::handled(kind, self.fee_handler.handle_toll_collected(event, meta)).await?;
             }
-            x if x == fee_router::BuybackExecuted::SIGNATURE_HASH.as_slice() => {
-                let event = Self::decode_event::<fee_router::BuybackExecuted>(&log.inner)?;
-                self.fee_handler
-                    .handle_buyback_executed(event, meta)
-                    .await?;
-                Ok(true)
+            EventKind::BuybackExecuted => {
+                let event = self.decode::<fee_router::BuybackExecuted>(kind, log)?;
+                Self::handled(kind, self.fee_handler.handle_buyback_executed(event, meta)).await?;
             }
-            x if x == fee_router::OperationsWithdrawn::SIGNATURE_HASH.as_slice() => {
-                let event = Self::decode_event::<fee_router::OperationsWithdrawn>(&log.inner)?;
-                self.fee_handler
-                    .handle_operations_withdrawn(event, meta)
+            EventKind::OperationsWithdrawn => {
+                let event = self.decode::<fee_router::OperationsWithdrawn>(kind, log)?;
+                Self::handled(kind, self.fee_handler.handle_operations_withdrawn(event, meta))
                     .await?;
-                Ok(true)
             }
 
             // ═══════════════════════════════════════════════════════════════════
             // REWARDS DISTRIBUTOR EVENTS (3 events → EmissionsPort)
             // ═══════════════════════════════════════════════════════════════════
-            x if x == rewards_distributor::EmissionsDistributed::SIGNATURE_HASH.as_slice() => {
-                let event =
-                    Self::decode_event::<rewards_distributor::EmissionsDistributed>(&log.inner)?;
-                self.emissions_handler
-                    .handle_emissions_distributed(event, meta)
-                    .await?;
-                Ok(true)
+            EventKind::EmissionsDistributed => {
+                let event = self.decode::<rewards_distributor::EmissionsDistributed>(kind, log)?;
+                Self::handled(
+                    kind,
+                    self.emissions_handler.handle_emissions_distributed(event, meta),
+                )
+                .await?;
             }
-            x if x == rewards_distributor::WeightsUpdated::SIGNATURE_HASH.as_slice() => {
-                let event = Self::decode_event::<rewards_distributor::WeightsUpdated>(&log.inner)?;
-                self.emissions_handler
-                    .handle_weights_updated(event, meta)
+            EventKind::WeightsUpdated => {
+                let event = self.decode::<rewards_distributor::WeightsUpdated>(kind, log)?;
+                Self::handled(kind, self.emissions_handler.handle_weights_updated(event, meta))
                     .await?;
-                Ok(true)
             }
-            x if x == rewards_distributor::TokensClaimed::SIGNATURE_HASH.as_slice() => {
-                let event = Self::decode_event::<rewards_distributor::TokensClaimed>(&log.inner)?;
-                self.emissions_handler
-                    .handle_tokens_claimed(event, meta)
+            EventKind::TokensClaimed => {
+                let event = self.decode::<rewards_distributor::TokensClaimed>(kind, log)?;
+                Self::handled(kind, self.emissions_handler.handle_tokens_claimed(event, meta))
                     .await?;
-                Ok(true)
             }
+        }
 
-            // ═══════════════════════════════════════════════════════════════════
-            // UNKNOWN EVENTS
-            // ═══════════════════════════════════════════════════════════════════
-            _ => {
-                warn!(
-                    topic0 = ?topic0,
-                    contract = ?meta.contract,
-                    "Unknown event signature - not a GHOSTNET event"
-                );
-                Ok(false)
-            }
+        Ok(())
+    }
+
+    /// Await a handler call, recording its outcome.
+    async fn handled(kind: EventKind, handling: impl Future<Output = Result<()>>) -> Result<()> {
+        let result = handling.await;
+        obs::record_handler_result(kind.handler(), result.is_ok());
+        result
+    }
+
+    /// Get routing counters.
+    #[must_use]
+    pub fn stats(&self) -> RouterStats {
+        RouterStats {
+            decoded: self.decoded.load(Ordering::Relaxed),
+            unknown: self.unknown.load(Ordering::Relaxed),
+            failed_decode: self.failed_decode.load(Ordering::Relaxed),
         }
     }
 
@@ -379,24 +393,56 @@ where
     ///
     /// Uses Alloy's `decode_log` which returns a `Log<Ev>` wrapper.
     /// We extract the inner event data from it.
-    fn decode_event<Ev: SolEvent>(log: &PrimitiveLog) -> Result<Ev> {
-        let decoded = Ev::decode_log(log).map_err(|e| {
-            AppError::Infra(InfraError::EventDecoding(format!(
-                "Failed to decode {}: {e}",
-                Ev::SIGNATURE
-            )))
-        })?;
-        Ok(decoded.data)
+    ///
+    /// A known topic that fails to decode means the ABI has drifted from the
+    /// deployed contract, so every such log would fail the same way. Only the
+    /// first failure per event kind is logged.
+    fn decode<Ev: SolEvent>(&self, kind: EventKind, log: &Log) -> Result<Ev> {
+        match Ev::decode_log(&log.inner) {
+            Ok(decoded) => {
+                self.decoded.fetch_add(1, Ordering::Relaxed);
+                obs::record_event_decoded(kind.contract());
+                Ok(decoded.data)
+            }
+            Err(e) => {
+                self.failed_decode.fetch_add(1, Ordering::Relaxed);
+                let bit = 1 << kind as u32;
+                if self.decode_warned.fetch_or(bit, Ordering::Relaxed) & bit == 0 {
+                    error!(
+                        event = Ev::SIGNATURE,
+                        tx_hash = ?log.transaction_hash,
+                        error = %e,
+                        "Failed to decode known event - possible ABI drift"
+                    );
+                }
+                Err(AppError::Infra(InfraError::EventDecoding(format!(
+                    "Failed to decode {}: {e}",
+                    Ev::SIGNATURE
+                ))))
+            }
+        }
     }
 }
 
+/// Routing counters for the health endpoint.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct RouterStats {
+    /// Logs decoded as a known event.
+    pub decoded: u64,
+    /// Logs with an unknown topic0.
+    pub unknown: u64,
+    /// Logs with a known topic0 that failed to decode.
+    pub failed_decode: u64,
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // TESTS
 // ═══════════════════════════════════════════════════════════════════════════════
 
 #[cfg(test)]
+#[allow(clippy::expect_used)]
 mod tests {
-    use alloy::primitives::{Address, B256};
+    use alloy::primitives::{Address, B256, Bytes, Log as PrimitiveLog, LogData, U256};
     use chrono::Utc;
 
     use super::*;
@@ -411,6 +457,9 @@ mod tests {
             log_index: 0,
             timestamp: Utc::now(),
             contract: Address::ZERO,
+            chain_id: 6343,
+            tx_function: None,
+            tx_from: None,
         }
     }
 
@@ -513,4 +562,74 @@ mod tests {
             "unknown signature should return false"
         );
     }
+
+    fn log_with(data: LogData) -> Log {
+        Log {
+            inner: PrimitiveLog {
+                address: Address::ZERO,
+                data,
+            },
+            transaction_hash: Some(B256::repeat_byte(0x11)),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn known_event_is_decoded_and_counted() {
+        let router = create_test_router();
+        let transfer = data_token::Transfer {
+            from: Address::repeat_byte(1),
+            to: Address::repeat_byte(2),
+            value: U256::from(100),
+        };
+
+        let handled = router
+            .route_log(&log_with(transfer.encode_log_data()), sample_metadata())
+            .await
+            .expect("transfer should route");
+
+        assert!(handled);
+        assert_eq!(router.token_handler.count(), 1);
+        assert_eq!(
+            router.stats(),
+            RouterStats {
+                decoded: 1,
+                unknown: 0,
+                failed_decode: 0,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn routing_counters_track_unknown_and_failed_logs() {
+        let router = create_test_router();
+
+        let unknown = LogData::new_unchecked(vec![B256::repeat_byte(0xFF)], Bytes::new());
+        assert!(!router.route_log(&log_with(unknown), sample_metadata()).await.expect("ok"));
+
+        // A known topic without its indexed topics and data (ABI drift)
+        let drifted = LogData::new_unchecked(
+            vec![ghost_core::JackedIn::SIGNATURE_HASH],
+            Bytes::new(),
+        );
+        for _ in 0..3 {
+            let result = router.route_log(&log_with(drifted.clone()), sample_metadata()).await;
+            assert!(result.is_err());
+        }
+
+        assert_eq!(router.position_handler.count(), 0);
+        assert_eq!(
+            router.stats(),
+            RouterStats {
+                decoded: 0,
+                unknown: 1,
+                failed_decode: 3,
+            }
+        );
+        // Logged once for the event kind, not once per log
+        assert_eq!(
+            router.decode_warned.load(Ordering::Relaxed),
+            1 << EventKind::JackedIn as u32
+        );
+    }
 }
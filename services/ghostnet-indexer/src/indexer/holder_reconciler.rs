@@ -0,0 +1,467 @@
+//! Periodic reconciliation of holder balances with the chain.
+//!
+//! Holder balances are derived from `Transfer` events, so a missed or
+//! orphaned transfer leaves them off for good. The [`HolderReconciler`]
+//! reads `balanceOf` for a sample of holders every `reconcile_interval` and
+//! corrects those that drifted:
+//!
+//! 1. Holders flagged by an underflow or a reorg rollback, then those
+//!    compared longest ago, up to `reconcile_sample_size`
+//! 2. `balanceOf` at the last indexed block, so the balance read covers
+//!    exactly the transfers indexed so far
+//! 3. A conditional write, skipped if a newer transfer was applied to the
+//!    holder in the meantime
+//!
+//! Holders touched past the last indexed block (in a batch not committed
+//! yet, or orphaned by a reorg) are skipped until the indexer catches up.
+//! Every run also resets the holder count gauge from the store.
+//!
+//! ```text
+//! ticker ──▶ HolderReconciler ──▶ HolderStore (sample) ──▶ balanceOf @ last block
+//!                    │                                           │
+//!                    └──▶ HolderStore::reconcile_holder ◀────────┘
+//! ```
+
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use alloy::primitives::{Address, U256};
+use alloy::providers::{DynProvider, Provider};
+use alloy::rpc::types::TransactionRequest;
+use alloy::sol_types::SolCall;
+use bigdecimal::ToPrimitive;
+use chrono::Utc;
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, instrument, warn};
+
+use crate::abi::data_token;
+use crate::config::HolderSettings;
+use crate::error::{InfraError, Result};
+use crate::obs;
+use crate::ports::{HolderStore, IndexerStateStore};
+use crate::types::primitives::{BlockNumber, EthAddress, TokenAmount};
+
+/// Decimals of the DATA token.
+const DATA_TOKEN_DECIMALS: u8 = 18;
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// REPORT
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Outcome of one reconciliation run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReconcileReport {
+    /// Block the balances were read at.
+    pub block: BlockNumber,
+    /// Holders compared with the chain.
+    pub checked: usize,
+    /// Holders whose balance was corrected.
+    pub corrected: usize,
+    /// Holders not compared, because they were touched past `block` or while
+    /// their balance was read.
+    pub skipped: usize,
+    /// Sum of the corrections, in wei.
+    pub drift: U256,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// HOLDER RECONCILER
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Compares holder balances with `balanceOf`, and corrects what drifted.
+pub struct HolderReconciler<S> {
+    store: Arc<S>,
+    provider: DynProvider,
+    data_token: Address,
+    /// Interval between background reconciliations.
+    interval: Duration,
+    /// Holders compared per run.
+    sample_size: u32,
+}
+
+impl<S> fmt::Debug for HolderReconciler<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HolderReconciler")
+            .field("data_token", &self.data_token)
+            .field("interval", &self.interval)
+            .field("sample_size", &self.sample_size)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S> HolderReconciler<S>
+where
+    S: HolderStore + IndexerStateStore,
+{
+    /// Create a reconciler reading `DataToken` at `data_token`.
+    #[must_use]
+    pub fn new<P>(
+        store: Arc<S>,
+        provider: P,
+        data_token: Address,
+        settings: &HolderSettings,
+    ) -> Self
+    where
+        P: Provider + 'static,
+    {
+        Self {
+            store,
+            provider: provider.erased(),
+            data_token,
+            interval: settings.reconcile_interval(),
+            sample_size: settings.reconcile_sample_size.max(1),
+        }
+    }
+
+    /// Compare a sample of holders with the chain at the last indexed block,
+    /// correcting those that drifted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store cannot be read or written, or a
+    /// `balanceOf` call fails. Holders corrected before the failure stay
+    /// corrected.
+    #[instrument(skip(self))]
+    pub async fn reconcile_once(&self) -> Result<ReconcileReport> {
+        let block = self.store.get_last_block().await?;
+        let mut report = ReconcileReport {
+            block,
+            checked: 0,
+            corrected: 0,
+            skipped: 0,
+            drift: U256::ZERO,
+        };
+
+        let holders = self
+            .store
+            .get_holders_to_reconcile(self.sample_size)
+            .await?;
+        for holder in holders {
+            if holder.last_block > block {
+                report.skipped += 1;
+                obs::record_holder_reconciliation("skipped");
+                continue;
+            }
+
+            let balance = self.balance_of(holder.address, block).await?;
+            if !self
+                .store
+                .reconcile_holder(&holder, balance, Utc::now())
+                .await?
+            {
+                report.skipped += 1;
+                obs::record_holder_reconciliation("skipped");
+                continue;
+            }
+
+            report.checked += 1;
+            let drift = balance.max(holder.balance) - balance.min(holder.balance);
+            if drift.is_zero() {
+                obs::record_holder_reconciliation("match");
+                continue;
+            }
+
+            let tokens = TokenAmount::from_wei(drift, DATA_TOKEN_DECIMALS);
+            warn!(
+                holder = %holder.address,
+                indexed = %holder.balance,
+                on_chain = %balance,
+                drift = %tokens,
+                block = block.value(),
+                "Holder balance drifted, corrected from chain"
+            );
+            report.corrected += 1;
+            report.drift = report.drift.saturating_add(drift);
+            obs::record_holder_reconciliation("corrected");
+            obs::record_holder_drift(tokens.as_decimal().to_f64().unwrap_or(f64::MAX));
+        }
+
+        obs::set_token_holders(self.store.count_holders().await?);
+        Ok(report)
+    }
+
+    /// Spawn the background reconciliation task.
+    ///
+    /// Reconciles immediately, then every `reconcile_interval` until
+    /// `shutdown` is cancelled.
+    pub fn spawn_reconcile_task(self: &Arc<Self>, shutdown: CancellationToken) -> JoinHandle<()>
+    where
+        S: 'static,
+    {
+        let reconciler = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(reconciler.interval);
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+            loop {
+                tokio::select! {
+                    () = shutdown.cancelled() => break,
+                    _ = ticker.tick() => {}
+                }
+                match reconciler.reconcile_once().await {
+                    Ok(report) if report.corrected > 0 => info!(
+                        checked = report.checked,
+                        corrected = report.corrected,
+                        skipped = report.skipped,
+                        "Holder balances reconciled"
+                    ),
+                    Ok(report) => debug!(
+                        checked = report.checked,
+                        skipped = report.skipped,
+                        "Holder balances reconciled"
+                    ),
+                    Err(e) => warn!(error = %e, "Holder reconciliation failed"),
+                }
+            }
+        })
+    }
+
+    /// Read the DATA balance of `holder` at `block`.
+    async fn balance_of(&self, holder: EthAddress, block: BlockNumber) -> Result<U256> {
+        let call = data_token::balanceOfCall {
+            account: Address::from(holder),
+        };
+        let request = TransactionRequest::default()
+            .to(self.data_token)
+            .input(call.abi_encode().into());
+        let output = self
+            .provider
+            .call(request)
+            .block(block.value().into())
+            .await
+            .map_err(|e| InfraError::Rpc(Box::new(e)))?;
+
+        data_token::balanceOfCall::abi_decode_returns(&output)
+            .map_err(|e| InfraError::EventDecoding(format!("balanceOf: {e}")).into())
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::significant_drop_tightening)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    use alloy::primitives::{B256, Bytes};
+    use alloy::providers::ProviderBuilder;
+    use alloy::transports::mock::Asserter;
+    use async_trait::async_trait;
+    use chrono::DateTime;
+
+    use super::*;
+    use crate::indexer::Contract;
+    use crate::types::entities::{HolderBalance, HolderSort};
+
+    /// Holder store keeping balances in memory, indexed up to `last_block`.
+    #[derive(Debug)]
+    struct HolderBook {
+        last_block: BlockNumber,
+        holders: Mutex<HashMap<EthAddress, HolderBalance>>,
+    }
+
+    impl HolderBook {
+        fn new(last_block: u64, holders: impl IntoIterator<Item = HolderBalance>) -> Self {
+            Self {
+                last_block: BlockNumber::new(last_block),
+                holders: Mutex::new(holders.into_iter().map(|h| (h.address, h)).collect()),
+            }
+        }
+
+        fn holder(&self, address: EthAddress) -> HolderBalance {
+            self.holders.lock().unwrap()[&address].clone()
+        }
+    }
+
+    #[async_trait]
+    impl HolderStore for HolderBook {
+        async fn get_holder(&self, address: &EthAddress) -> Result<Option<HolderBalance>> {
+            Ok(self.holders.lock().unwrap().get(address).cloned())
+        }
+
+        async fn save_holder(&self, holder: &HolderBalance) -> Result<()> {
+            self.holders
+                .lock()
+                .unwrap()
+                .insert(holder.address, holder.clone());
+            Ok(())
+        }
+
+        async fn get_top_holders(&self, _: HolderSort, _: u32) -> Result<Vec<HolderBalance>> {
+            Ok(Vec::new())
+        }
+
+        async fn count_holders(&self) -> Result<u64> {
+            let holders = self.holders.lock().unwrap();
+            Ok(holders.values().filter(|h| h.is_holder()).count() as u64)
+        }
+
+        async fn get_holders_to_reconcile(&self, limit: u32) -> Result<Vec<HolderBalance>> {
+            let mut holders: Vec<_> = self.holders.lock().unwrap().values().cloned().collect();
+            holders.sort_by_key(|h| (!h.needs_reconciliation, h.reconciled_at, *h.address.as_bytes()));
+            holders.truncate(limit as usize);
+            Ok(holders)
+        }
+
+        async fn reconcile_holder(
+            &self,
+            holder: &HolderBalance,
+            balance: U256,
+            at: DateTime<Utc>,
+        ) -> Result<bool> {
+            let mut holders = self.holders.lock().unwrap();
+            let stored = holders.get_mut(&holder.address).unwrap();
+            if (stored.last_block, stored.last_log_index)
+                != (holder.last_block, holder.last_log_index)
+            {
+                return Ok(false);
+            }
+            stored.balance = balance;
+            stored.needs_reconciliation = false;
+            stored.reconciled_at = Some(at);
+            Ok(true)
+        }
+    }
+
+    #[async_trait]
+    impl IndexerStateStore for HolderBook {
+        async fn get_last_block(&self) -> Result<BlockNumber> {
+            Ok(self.last_block)
+        }
+
+        async fn set_last_block(&self, _block: BlockNumber, _hash: B256) -> Result<()> {
+            Ok(())
+        }
+
+        async fn insert_block_hash(
+            &self,
+            _block: BlockNumber,
+            _hash: B256,
+            _parent: B256,
+            _timestamp: u64,
+        ) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_block_hash(&self, _block: BlockNumber) -> Result<Option<B256>> {
+            Ok(None)
+        }
+
+        async fn execute_reorg_rollback(&self, _fork_point: BlockNumber) -> Result<()> {
+            Ok(())
+        }
+
+        async fn prune_old_blocks(&self, _keep_blocks: u64) -> Result<u64> {
+            Ok(0)
+        }
+
+        async fn get_cursor(&self, _contract: Contract) -> Result<Option<BlockNumber>> {
+            Ok(None)
+        }
+
+        async fn set_cursor(&self, _contract: Contract, _block: BlockNumber) -> Result<()> {
+            Ok(())
+        }
+
+        async fn min_cursor(&self, _contracts: &[Contract]) -> Result<Option<BlockNumber>> {
+            Ok(None)
+        }
+
+        async fn commit_batch(&self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn discard_batch(&self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    fn holder(byte: u8, balance: u64, last_block: u64, flagged: bool) -> HolderBalance {
+        let mut holder = HolderBalance::new(EthAddress::new([byte; 20]), Utc::now());
+        holder.balance = U256::from(balance);
+        holder.last_block = BlockNumber::new(last_block);
+        holder.needs_reconciliation = flagged;
+        holder
+    }
+
+    fn balance(value: u64) -> Bytes {
+        data_token::balanceOfCall::abi_encode_returns(&U256::from(value)).into()
+    }
+
+    fn reconciler(book: &Arc<HolderBook>, asserter: Asserter) -> HolderReconciler<HolderBook> {
+        let provider = ProviderBuilder::new().connect_mocked_client(asserter);
+        HolderReconciler::new(
+            book.clone(),
+            provider,
+            Address::repeat_byte(0xda),
+            &HolderSettings::default(),
+        )
+    }
+
+    #[tokio::test]
+    async fn drifted_balances_are_corrected() {
+        // A flagged holder clamped to zero, then two unflagged ones
+        let book = Arc::new(HolderBook::new(
+            100,
+            [
+                holder(1, 0, 90, true),
+                holder(2, 50, 80, false),
+                holder(3, 70, 80, false),
+            ],
+        ));
+        let asserter = Asserter::new();
+        asserter.push_success(&balance(40));
+        asserter.push_success(&balance(50));
+        asserter.push_success(&balance(65));
+
+        let report = reconciler(&book, asserter).reconcile_once().await.unwrap();
+
+        assert_eq!(report.block, BlockNumber::new(100));
+        assert_eq!(
+            (report.checked, report.corrected, report.skipped),
+            (3, 2, 0)
+        );
+        assert_eq!(report.drift, U256::from(45));
+
+        let flagged = book.holder(EthAddress::new([1; 20]));
+        assert_eq!(flagged.balance, U256::from(40));
+        assert!(!flagged.needs_reconciliation);
+        assert!(flagged.reconciled_at.is_some());
+        assert_eq!(
+            book.holder(EthAddress::new([3; 20])).balance,
+            U256::from(65)
+        );
+    }
+
+    #[tokio::test]
+    async fn holders_touched_past_the_last_block_are_skipped() {
+        let book = Arc::new(HolderBook::new(100, [holder(1, 10, 101, true)]));
+
+        // No balanceOf call is made, so the mock has no response queued
+        let report = reconciler(&book, Asserter::new())
+            .reconcile_once()
+            .await
+            .unwrap();
+
+        assert_eq!((report.checked, report.skipped), (0, 1));
+        let holder = book.holder(EthAddress::new([1; 20]));
+        assert_eq!(holder.balance, U256::from(10));
+        assert!(holder.needs_reconciliation);
+    }
+
+    #[tokio::test]
+    async fn failed_read_leaves_the_holder_flagged() {
+        let book = Arc::new(HolderBook::new(100, [holder(1, 0, 90, true)]));
+        let asserter = Asserter::new();
+        asserter.push_failure_msg("execution reverted");
+
+        assert!(reconciler(&book, asserter).reconcile_once().await.is_err());
+        assert!(book.holder(EthAddress::new([1; 20])).needs_reconciliation);
+    }
+}
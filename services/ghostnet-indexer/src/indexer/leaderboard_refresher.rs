@@ -0,0 +1,293 @@
+//! Periodic leaderboard computation.
+//!
+//! Leaderboards aggregate the whole `positions` table, so the
+//! [`LeaderboardRefresher`] recomputes them in the background every
+//! `refresh_interval` and stores the results in the [`MemoryCache`]. API
+//! requests are served from the cache; a store query only happens on a cold
+//! cache (before the first refresh, or after a TTL expiry).
+//!
+//! ```text
+//! ticker ──▶ LeaderboardRefresher ──▶ LeaderboardStore (top max_entries)
+//!                     │
+//!                     └──▶ MemoryCache::set_leaderboard ◀── GET /leaderboard/:type
+//! ```
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, instrument, warn};
+
+use crate::config::LeaderboardSettings;
+use crate::error::Result;
+use crate::ports::LeaderboardStore;
+use crate::store::MemoryCache;
+use crate::types::entities::LeaderboardEntry;
+use crate::types::enums::LeaderboardType;
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// LEADERBOARD REFRESHER
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Keeps cached leaderboards fresh and serves them to readers.
+#[derive(Debug)]
+pub struct LeaderboardRefresher<S> {
+    /// Store the leaderboards are computed from.
+    store: Arc<S>,
+    /// Cache the computed leaderboards are served from.
+    cache: Arc<MemoryCache>,
+    /// Interval between background refreshes.
+    refresh_interval: Duration,
+    /// Entries computed per leaderboard.
+    max_entries: u32,
+}
+
+impl<S: LeaderboardStore> LeaderboardRefresher<S> {
+    /// Create a new refresher.
+    #[must_use]
+    pub fn new(store: Arc<S>, cache: Arc<MemoryCache>, settings: &LeaderboardSettings) -> Self {
+        Self {
+            store,
+            cache,
+            refresh_interval: settings.refresh_interval(),
+            max_entries: settings.max_entries.max(1),
+        }
+    }
+
+    /// Maximum number of entries available per leaderboard.
+    #[must_use]
+    pub const fn max_entries(&self) -> u32 {
+        self.max_entries
+    }
+
+    /// Get the top `limit` entries of a leaderboard.
+    ///
+    /// Served from the cache; on a miss the leaderboard is computed once and
+    /// cached. `limit` is capped at [`Self::max_entries`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the leaderboard is not cached and the store query fails.
+    pub async fn get(
+        &self,
+        leaderboard: LeaderboardType,
+        limit: u32,
+    ) -> Result<Vec<LeaderboardEntry>> {
+        let mut entries = match self.cache.get_leaderboard(leaderboard.as_str()) {
+            Some(entries) => entries,
+            None => self.refresh(leaderboard).await?,
+        };
+        entries.truncate(limit.min(self.max_entries) as usize);
+        Ok(entries)
+    }
+
+    /// Recompute a leaderboard and replace its cached entries.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store query fails. The cached entries are left
+    /// untouched in that case.
+    #[instrument(skip(self))]
+    pub async fn refresh(&self, leaderboard: LeaderboardType) -> Result<Vec<LeaderboardEntry>> {
+        let entries = self
+            .store
+            .get_leaderboard(leaderboard, self.max_entries)
+            .await?;
+        self.cache
+            .set_leaderboard(leaderboard.as_str(), entries.clone());
+        debug!(%leaderboard, entries = entries.len(), "Leaderboard refreshed");
+        Ok(entries)
+    }
+
+    /// Recompute every leaderboard, returning how many were refreshed.
+    ///
+    /// A failing leaderboard is logged and keeps its previous cached entries;
+    /// the others are still refreshed.
+    pub async fn refresh_all(&self) -> usize {
+        let mut refreshed = 0;
+        for leaderboard in LeaderboardType::ALL {
+            match self.refresh(leaderboard).await {
+                Ok(_) => refreshed += 1,
+                Err(e) => warn!(%leaderboard, error = %e, "Leaderboard refresh failed"),
+            }
+        }
+        refreshed
+    }
+
+    /// Spawn the background refresh task.
+    ///
+    /// Refreshes immediately, then every `refresh_interval` until `shutdown`
+    /// is cancelled.
+    pub fn spawn_refresh_task(self: &Arc<Self>, shutdown: CancellationToken) -> JoinHandle<()>
+    where
+        S: 'static,
+    {
+        let refresher = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(refresher.refresh_interval);
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+            loop {
+                tokio::select! {
+                    () = shutdown.cancelled() => break,
+                    _ = ticker.tick() => {}
+                }
+                refresher.refresh_all().await;
+            }
+        })
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+    use async_trait::async_trait;
+    use bigdecimal::BigDecimal;
+
+    use super::*;
+    use crate::error::InfraError;
+    use crate::ports::Cache;
+    use crate::types::primitives::{EthAddress, TokenAmount};
+
+    /// Store returning up to ten descending entries and counting queries.
+    #[derive(Debug, Default)]
+    struct CountingStore {
+        queries: AtomicUsize,
+        fail: AtomicBool,
+    }
+
+    #[async_trait]
+    impl LeaderboardStore for CountingStore {
+        #[allow(clippy::cast_possible_truncation)]
+        async fn get_leaderboard(
+            &self,
+            _leaderboard: LeaderboardType,
+            limit: u32,
+        ) -> Result<Vec<LeaderboardEntry>> {
+            self.queries.fetch_add(1, Ordering::SeqCst);
+            if self.fail.load(Ordering::SeqCst) {
+                return Err(InfraError::Internal("injected failure".into()).into());
+            }
+            Ok((1..=limit.min(10))
+                .map(|rank| LeaderboardEntry {
+                    rank,
+                    user_address: EthAddress::new([rank as u8; 20]),
+                    score: TokenAmount::new(BigDecimal::from(100 - rank)).unwrap(),
+                    metadata: None,
+                })
+                .collect())
+        }
+    }
+
+    fn refresher(max_entries: u32) -> (LeaderboardRefresher<CountingStore>, Arc<CountingStore>) {
+        let store = Arc::new(CountingStore::default());
+        let settings = LeaderboardSettings {
+            refresh_interval_ms: 3_600_000,
+            default_limit: max_entries,
+            max_entries,
+        };
+        let refresher =
+            LeaderboardRefresher::new(Arc::clone(&store), Arc::new(MemoryCache::new()), &settings);
+        (refresher, store)
+    }
+
+    #[tokio::test]
+    async fn get_serves_from_cache_after_first_miss() {
+        let (refresher, store) = refresher(5);
+
+        let first = refresher
+            .get(LeaderboardType::GhostStreak, 3)
+            .await
+            .unwrap();
+        let second = refresher
+            .get(LeaderboardType::GhostStreak, 5)
+            .await
+            .unwrap();
+
+        assert_eq!(store.queries.load(Ordering::SeqCst), 1);
+        assert_eq!(first.len(), 3);
+        assert_eq!(second.len(), 5);
+        assert_eq!(second[0].rank, 1);
+        assert_eq!(refresher.cache.stats().hits, 1);
+    }
+
+    #[tokio::test]
+    async fn limit_is_capped_at_max_entries() {
+        let (refresher, store) = refresher(4);
+
+        let entries = refresher
+            .get(LeaderboardType::TotalExtracted, 500)
+            .await
+            .unwrap();
+
+        assert_eq!(entries.len(), 4);
+        assert_eq!(store.queries.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn refresh_all_populates_every_leaderboard() {
+        let (refresher, store) = refresher(5);
+
+        assert_eq!(refresher.refresh_all().await, LeaderboardType::ALL.len());
+        for leaderboard in LeaderboardType::ALL {
+            refresher.get(leaderboard, 5).await.unwrap();
+        }
+
+        assert_eq!(
+            store.queries.load(Ordering::SeqCst),
+            LeaderboardType::ALL.len()
+        );
+    }
+
+    #[tokio::test]
+    async fn failed_refresh_keeps_cached_entries() {
+        let (refresher, store) = refresher(5);
+        refresher.refresh_all().await;
+
+        store.fail.store(true, Ordering::SeqCst);
+        assert_eq!(refresher.refresh_all().await, 0);
+
+        let entries = refresher
+            .get(LeaderboardType::BiggestExtraction, 5)
+            .await
+            .unwrap();
+        assert_eq!(entries.len(), 5);
+    }
+
+    #[tokio::test]
+    async fn refresh_task_populates_cache() {
+        let store = Arc::new(CountingStore::default());
+        let refresher = Arc::new(LeaderboardRefresher::new(
+            Arc::clone(&store),
+            Arc::new(MemoryCache::new()),
+            &LeaderboardSettings::default(),
+        ));
+        let shutdown = CancellationToken::new();
+        let handle = refresher.spawn_refresh_task(shutdown.clone());
+
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while store.queries.load(Ordering::SeqCst) < LeaderboardType::ALL.len() {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+        })
+        .await
+        .unwrap();
+        shutdown.cancel();
+        handle.await.unwrap();
+
+        assert!(
+            refresher
+                .cache
+                .get_leaderboard(LeaderboardType::GhostStreak.as_str())
+                .is_some()
+        );
+    }
+}
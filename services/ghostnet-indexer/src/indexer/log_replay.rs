@@ -0,0 +1,642 @@
+//! Replaying archived raw logs through the handlers.
+//!
+//! With `raw_logs.enabled`, the [`Pipeline`](super::Pipeline) archives every
+//! log before routing it. [`LogReplayer`] reads the archived logs of a block
+//! range back, in chain order, and routes them through the handlers again,
+//! so a fixed handler can rebuild its tables without refetching the range
+//! from RPC.
+//!
+//! # Scope
+//!
+//! A replay runs only the handlers it names, as by [`EventKind::handler`];
+//! logs of the others are skipped. The position and death handlers build
+//! positions from their history of changes, and the death handler updates
+//! positions the position handler created, so they are replayed together and
+//! only over cleared tables (see [`LogReplayer::with_truncate_derived`]).
+//! The other handlers overwrite or skip what they already wrote.
+//!
+//! # Progress
+//!
+//! The store's batch is committed at every block boundary together with the
+//! replay's progress, so a stopped replay resumes after the last block it
+//! committed. Progress is kept per block range and handler set, and removed
+//! once the replay finishes.
+//!
+//! ```ignore
+//! let replayer = LogReplayer::new(Arc::new(store.batched()), router, &["position", "death"])?
+//!     .with_truncate_derived(true)
+//!     .with_shutdown(shutdown);
+//! let report = replayer.replay(BlockNumber::new(100), BlockNumber::new(200)).await?;
+//! ```
+
+use std::sync::Arc;
+
+use alloy::rpc::types::Log;
+use async_trait::async_trait;
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info, instrument};
+
+use crate::error::{AppError, Result};
+use crate::ports::{IndexerStateStore, RawLogStore};
+use crate::types::events::EventMetadata;
+use crate::types::primitives::BlockNumber;
+
+use super::{EventKind, LogRouter};
+
+/// Logs read from the archive per page.
+const DEFAULT_PAGE_SIZE: u32 = 1_000;
+
+/// Handlers that can only be replayed over cleared tables, and together.
+const REBUILT_HANDLERS: [&str; 2] = ["position", "death"];
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// SCOPED ROUTER
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Router that passes on only the logs of the given handlers.
+#[derive(Debug, Clone)]
+pub struct ScopedRouter<R> {
+    router: R,
+    handlers: Vec<&'static str>,
+}
+
+impl<R> ScopedRouter<R> {
+    /// Route the logs of `handlers` through `router`, skipping the rest.
+    pub const fn new(router: R, handlers: Vec<&'static str>) -> Self {
+        Self { router, handlers }
+    }
+
+    /// Check if the log's event is routed to one of the handlers.
+    fn includes(&self, log: &Log) -> bool {
+        log.topic0()
+            .and_then(EventKind::from_topic)
+            .is_some_and(|kind| self.handlers.contains(&kind.handler()))
+    }
+}
+
+#[async_trait]
+impl<R: LogRouter> LogRouter for ScopedRouter<R> {
+    async fn route_log(&self, log: &Log, meta: EventMetadata) -> Result<bool> {
+        if !self.includes(log) {
+            return Ok(false);
+        }
+        self.router.route_log(log, meta).await
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// REPORT
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Outcome of a replay.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReplayReport {
+    /// Last block already replayed when the replay resumed, if it did.
+    pub resumed_after: Option<BlockNumber>,
+    /// Rows deleted from the derived tables before replaying.
+    pub rows_cleared: u64,
+    /// Logs routed to a handler.
+    pub routed: u64,
+    /// Logs of other handlers, or of unknown events.
+    pub skipped: u64,
+    /// Logs whose handler failed; logged and skipped, as when indexing.
+    pub failed: u64,
+    /// Last block whose logs have all been replayed.
+    pub last_block: Option<BlockNumber>,
+    /// Whether the whole range was replayed, rather than stopped by shutdown.
+    pub complete: bool,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// LOG REPLAYER
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Routes archived logs of a block range through the handlers again.
+///
+/// # Type Parameters
+///
+/// * `S` - Store holding the archive, batching the handlers' writes
+/// * `R` - Router to the handlers, without the event outbox so replayed
+///   events are not published again
+#[derive(Debug)]
+pub struct LogReplayer<S, R> {
+    store: Arc<S>,
+    router: ScopedRouter<R>,
+    truncate_derived: bool,
+    page_size: u32,
+    shutdown: CancellationToken,
+}
+
+impl<S, R> LogReplayer<S, R>
+where
+    S: RawLogStore + IndexerStateStore,
+    R: LogRouter,
+{
+    /// Create a replayer running `handlers`.
+    ///
+    /// `store` should batch writes, so a block is never committed in part.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a handler is unknown, none is given, or only one
+    /// of the position and death handlers is.
+    pub fn new(store: Arc<S>, router: R, handlers: &[&str]) -> Result<Self> {
+        let mut scope = Vec::new();
+        for name in handlers {
+            let handler = EventKind::ALL
+                .into_iter()
+                .map(EventKind::handler)
+                .find(|handler| handler == name)
+                .ok_or_else(|| AppError::Config(format!("Unknown handler: {name}")))?;
+            if !scope.contains(&handler) {
+                scope.push(handler);
+            }
+        }
+        if scope.is_empty() {
+            return Err(AppError::Config("No handlers to replay".into()));
+        }
+        let rebuilt = REBUILT_HANDLERS.iter().filter(|h| scope.contains(h)).count();
+        if rebuilt == 1 {
+            return Err(AppError::Config(
+                "The position and death handlers update the same positions; replay both".into(),
+            ));
+        }
+        scope.sort_unstable();
+
+        Ok(Self {
+            store,
+            router: ScopedRouter::new(router, scope),
+            truncate_derived: false,
+            page_size: DEFAULT_PAGE_SIZE,
+            shutdown: CancellationToken::new(),
+        })
+    }
+
+    /// Clear the derived tables of the handlers before replaying.
+    ///
+    /// Required for the position and death handlers. The tables are rebuilt
+    /// from the archive alone, so the replay must cover all of it.
+    #[must_use]
+    pub const fn with_truncate_derived(mut self, truncate: bool) -> Self {
+        self.truncate_derived = truncate;
+        self
+    }
+
+    /// Set the number of logs read from the archive at a time.
+    #[must_use]
+    pub const fn with_page_size(mut self, page_size: u32) -> Self {
+        self.page_size = page_size;
+        self
+    }
+
+    /// Stop at the next block boundary once `shutdown` is cancelled.
+    #[must_use]
+    pub fn with_shutdown(mut self, shutdown: CancellationToken) -> Self {
+        self.shutdown = shutdown;
+        self
+    }
+
+    /// Handlers the replay runs.
+    #[must_use]
+    pub fn handlers(&self) -> &[&'static str] {
+        &self.router.handlers
+    }
+
+    /// Key of the replay's progress: its block range and handlers.
+    fn key(&self, from: BlockNumber, to: BlockNumber) -> String {
+        format!("{from}-{to}:{}", self.router.handlers.join(","))
+    }
+
+    /// Replay the archived logs of blocks `from..=to`.
+    ///
+    /// Resumes after the last committed block if the same replay was stopped
+    /// before. Derived tables are only cleared when the replay starts afresh.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the replay would rebuild tables it does not clear
+    /// or cover, or if the store fails. Handler failures are logged and
+    /// counted instead.
+    #[instrument(skip(self), fields(from = from.value(), to = to.value()))]
+    pub async fn replay(&self, from: BlockNumber, to: BlockNumber) -> Result<ReplayReport> {
+        let key = self.key(from, to);
+        let mut report = ReplayReport {
+            resumed_after: self.store.get_replay_progress(&key).await?,
+            ..ReplayReport::default()
+        };
+
+        let rebuilds = REBUILT_HANDLERS
+            .iter()
+            .any(|h| self.router.handlers.contains(h));
+        if rebuilds && !self.truncate_derived {
+            return Err(AppError::Config(
+                "The position and death handlers do not overwrite their tables; \
+                 replay them with --truncate-derived"
+                    .into(),
+            ));
+        }
+        if self.truncate_derived && report.resumed_after.is_none() {
+            self.check_covers_archive(from, to).await?;
+            report.rows_cleared = self.store.clear_derived(&self.router.handlers).await?;
+            self.store.commit_batch().await?;
+            info!(rows = report.rows_cleared, "Cleared derived tables");
+        }
+
+        let start = report.resumed_after.map_or(from, |block| block.next());
+        info!(
+            key,
+            start = start.value(),
+            resumed = report.resumed_after.is_some(),
+            "Starting replay"
+        );
+        report.last_block = report.resumed_after;
+
+        let total_blocks = to.value().saturating_sub(start.value()) + 1;
+        let mut cursor = None;
+        let mut current: Option<BlockNumber> = None;
+        loop {
+            let logs = self.store.get_raw_logs(start, to, cursor, self.page_size).await?;
+            let Some(last) = logs.last() else {
+                break;
+            };
+            cursor = Some((last.block_number, last.tx_index, last.log_index));
+
+            for raw in &logs {
+                if let Some(block) = current
+                    && raw.block_number > block
+                {
+                    if self.shutdown.is_cancelled() {
+                        self.store.discard_batch().await?;
+                        info!(last_block = block.value(), "Replay stopped by shutdown");
+                        return Ok(report);
+                    }
+                    self.commit(&key, block, &mut report).await?;
+                }
+                current = Some(raw.block_number);
+
+                match self.router.route_log(&raw.log(), raw.metadata()).await {
+                    Ok(true) => report.routed += 1,
+                    Ok(false) => report.skipped += 1,
+                    Err(e) => {
+                        error!(
+                            block = raw.block_number.value(),
+                            tx_hash = %raw.tx_hash,
+                            error = %e,
+                            "Failed to replay log"
+                        );
+                        report.failed += 1;
+                    }
+                }
+            }
+
+            let replayed = cursor.map_or(0, |(block, ..)| block.value() - start.value() + 1);
+            // Precision loss is acceptable for progress percentage display
+            #[allow(clippy::cast_precision_loss)]
+            let progress = (replayed as f64 / total_blocks as f64) * 100.0;
+            info!(
+                through = cursor.map(|(block, ..)| block.value()),
+                routed = report.routed,
+                progress = format!("{:.1}%", progress),
+                "Replayed page"
+            );
+        }
+
+        if let Some(block) = current {
+            report.last_block = Some(block);
+        }
+        self.store.clear_replay_progress(&key).await?;
+        self.store.commit_batch().await?;
+        report.complete = true;
+
+        info!(
+            routed = report.routed,
+            skipped = report.skipped,
+            failed = report.failed,
+            "Replay complete"
+        );
+        Ok(report)
+    }
+
+    /// Commit the writes of every block through `block`, with the progress.
+    async fn commit(&self, key: &str, block: BlockNumber, report: &mut ReplayReport) -> Result<()> {
+        self.store.set_replay_progress(key, block).await?;
+        self.store.commit_batch().await?;
+        report.last_block = Some(block);
+        Ok(())
+    }
+
+    /// Check that `from..=to` covers the whole archive, which the cleared
+    /// tables are rebuilt from.
+    async fn check_covers_archive(&self, from: BlockNumber, to: BlockNumber) -> Result<()> {
+        let Some((first, last)) = self.store.raw_log_range().await? else {
+            return Err(AppError::Config("No raw logs archived to rebuild from".into()));
+        };
+        if from > first || to < last {
+            return Err(AppError::Config(format!(
+                "Cleared tables are rebuilt from the whole archive; replay blocks {first} to {last}"
+            )));
+        }
+        Ok(())
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::collections::HashMap;
+
+    use alloy::primitives::{Address, B256, Bytes};
+    use chrono::Utc;
+    use parking_lot::Mutex;
+
+    use super::*;
+    use crate::indexer::Contract;
+    use crate::types::entities::RawLog;
+
+    /// Archive and batch in memory.
+    ///
+    /// Progress is staged until the batch commits; every commit records the
+    /// progress it committed.
+    #[derive(Debug, Default)]
+    struct MemoryArchive {
+        logs: Vec<RawLog>,
+        progress: Mutex<HashMap<String, BlockNumber>>,
+        staged: Mutex<Option<(String, Option<BlockNumber>)>>,
+        commits: Mutex<Vec<Option<BlockNumber>>>,
+        cleared: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl RawLogStore for MemoryArchive {
+        async fn archive_logs(&self, _logs: &[RawLog]) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_raw_logs(
+            &self,
+            from: BlockNumber,
+            to: BlockNumber,
+            after: Option<(BlockNumber, u64, u64)>,
+            limit: u32,
+        ) -> Result<Vec<RawLog>> {
+            Ok(self
+                .logs
+                .iter()
+                .filter(|log| log.block_number >= from && log.block_number <= to)
+                .filter(|log| {
+                    let key = (log.block_number, log.tx_index, log.log_index);
+                    after.is_none_or(|after| key > after)
+                })
+                .take(limit as usize)
+                .cloned()
+                .collect())
+        }
+
+        async fn raw_log_range(&self) -> Result<Option<(BlockNumber, BlockNumber)>> {
+            Ok(self
+                .logs
+                .first()
+                .zip(self.logs.last())
+                .map(|(first, last)| (first.block_number, last.block_number)))
+        }
+
+        async fn get_replay_progress(&self, replay: &str) -> Result<Option<BlockNumber>> {
+            Ok(self.progress.lock().get(replay).copied())
+        }
+
+        async fn set_replay_progress(&self, replay: &str, block: BlockNumber) -> Result<()> {
+            *self.staged.lock() = Some((replay.into(), Some(block)));
+            Ok(())
+        }
+
+        async fn clear_replay_progress(&self, replay: &str) -> Result<()> {
+            *self.staged.lock() = Some((replay.into(), None));
+            Ok(())
+        }
+
+        async fn clear_derived(&self, handlers: &[&str]) -> Result<u64> {
+            self.cleared
+                .lock()
+                .extend(handlers.iter().map(ToString::to_string));
+            Ok(3)
+        }
+    }
+
+    #[async_trait]
+    impl IndexerStateStore for MemoryArchive {
+        async fn get_last_block(&self) -> Result<BlockNumber> {
+            Ok(BlockNumber::new(0))
+        }
+
+        async fn set_last_block(&self, _block: BlockNumber, _hash: B256) -> Result<()> {
+            Ok(())
+        }
+
+        async fn insert_block_hash(
+            &self,
+            _block: BlockNumber,
+            _hash: B256,
+            _parent: B256,
+            _timestamp: u64,
+        ) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_block_hash(&self, _block: BlockNumber) -> Result<Option<B256>> {
+            Ok(None)
+        }
+
+        async fn execute_reorg_rollback(&self, _fork_point: BlockNumber) -> Result<()> {
+            Ok(())
+        }
+
+        async fn prune_old_blocks(&self, _keep_blocks: u64) -> Result<u64> {
+            Ok(0)
+        }
+
+        async fn get_cursor(&self, _contract: Contract) -> Result<Option<BlockNumber>> {
+            Ok(None)
+        }
+
+        async fn set_cursor(&self, _contract: Contract, _block: BlockNumber) -> Result<()> {
+            Ok(())
+        }
+
+        async fn min_cursor(&self, _contracts: &[Contract]) -> Result<Option<BlockNumber>> {
+            Ok(None)
+        }
+
+        async fn commit_batch(&self) -> Result<()> {
+            let staged = self.staged.lock().take();
+            if let Some((replay, block)) = &staged {
+                let mut progress = self.progress.lock();
+                match block {
+                    Some(block) => progress.insert(replay.clone(), *block),
+                    None => progress.remove(replay),
+                };
+            }
+            self.commits.lock().push(staged.and_then(|(_, block)| block));
+            Ok(())
+        }
+
+        async fn discard_batch(&self) -> Result<()> {
+            *self.staged.lock() = None;
+            Ok(())
+        }
+    }
+
+    /// Router recording the block and event of every log it routes.
+    #[derive(Debug, Clone, Default)]
+    struct RecordingRouter {
+        routed: Arc<Mutex<Vec<(u64, EventKind)>>>,
+    }
+
+    #[async_trait]
+    impl LogRouter for RecordingRouter {
+        async fn route_log(&self, log: &Log, meta: EventMetadata) -> Result<bool> {
+            let kind = log.topic0().and_then(EventKind::from_topic).unwrap();
+            self.routed.lock().push((meta.block_number, kind));
+            Ok(true)
+        }
+    }
+
+    fn raw_log(block: u64, log_index: u64, kind: EventKind) -> RawLog {
+        RawLog {
+            address: Address::repeat_byte(0xc0),
+            chain_id: 6343,
+            topics: vec![kind.signature_hash()],
+            data: Bytes::new(),
+            block_number: BlockNumber::new(block),
+            block_hash: B256::left_padding_from(&block.to_be_bytes()),
+            tx_hash: B256::repeat_byte(0x11),
+            tx_index: 0,
+            log_index,
+            timestamp: Utc::now(),
+            tx_function: None,
+            tx_from: None,
+        }
+    }
+
+    /// Logs of blocks 1 to 3, for the position, scan and market handlers.
+    fn archive() -> MemoryArchive {
+        MemoryArchive {
+            logs: vec![
+                raw_log(1, 0, EventKind::JackedIn),
+                raw_log(1, 1, EventKind::ScanExecuted),
+                raw_log(2, 0, EventKind::RoundCreated),
+                raw_log(2, 1, EventKind::PositionCulled),
+                raw_log(3, 0, EventKind::ScanFinalized),
+            ],
+            ..MemoryArchive::default()
+        }
+    }
+
+    fn replayer(
+        store: &Arc<MemoryArchive>,
+        handlers: &[&str],
+    ) -> (LogReplayer<MemoryArchive, RecordingRouter>, RecordingRouter) {
+        let router = RecordingRouter::default();
+        let replayer = LogReplayer::new(store.clone(), router.clone(), handlers)
+            .unwrap()
+            .with_page_size(2);
+        (replayer, router)
+    }
+
+    #[tokio::test]
+    async fn replays_scoped_logs_in_order_committing_per_block() {
+        let store = Arc::new(archive());
+        let (replayer, router) = replayer(&store, &["scan", "market"]);
+
+        let report = replayer
+            .replay(BlockNumber::new(1), BlockNumber::new(3))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            *router.routed.lock(),
+            vec![
+                (1, EventKind::ScanExecuted),
+                (2, EventKind::RoundCreated),
+                (3, EventKind::ScanFinalized),
+            ]
+        );
+        assert_eq!((report.routed, report.skipped, report.failed), (3, 2, 0));
+        assert_eq!(report.last_block, Some(BlockNumber::new(3)));
+        assert!(report.complete);
+        // Blocks 1 and 2 commit at their boundaries; the end clears the progress
+        assert_eq!(
+            *store.commits.lock(),
+            vec![Some(BlockNumber::new(1)), Some(BlockNumber::new(2)), None]
+        );
+        assert!(store.progress.lock().is_empty());
+        assert!(store.cleared.lock().is_empty());
+    }
+
+    #[tokio::test]
+    async fn resumes_after_committed_block() {
+        let store = Arc::new(archive());
+        let (replayer, router) = replayer(&store, &["market", "scan"]);
+        store
+            .progress
+            .lock()
+            .insert("1-3:market,scan".into(), BlockNumber::new(1));
+
+        let report = replayer
+            .replay(BlockNumber::new(1), BlockNumber::new(3))
+            .await
+            .unwrap();
+
+        assert_eq!(report.resumed_after, Some(BlockNumber::new(1)));
+        assert_eq!(
+            *router.routed.lock(),
+            vec![(2, EventKind::RoundCreated), (3, EventKind::ScanFinalized)]
+        );
+        assert!(store.progress.lock().is_empty());
+    }
+
+    #[tokio::test]
+    async fn shutdown_keeps_progress_of_committed_blocks() {
+        let store = Arc::new(archive());
+        let (replayer, router) = replayer(&store, &["scan"]);
+        let shutdown = CancellationToken::new();
+        let replayer = replayer.with_shutdown(shutdown.clone());
+        shutdown.cancel();
+
+        let report = replayer
+            .replay(BlockNumber::new(1), BlockNumber::new(3))
+            .await
+            .unwrap();
+
+        // Block 1 was being replayed when the shutdown was seen
+        assert!(!report.complete);
+        assert_eq!(report.last_block, None);
+        assert_eq!(*router.routed.lock(), vec![(1, EventKind::ScanExecuted)]);
+        assert!(store.progress.lock().is_empty());
+    }
+
+    #[tokio::test]
+    async fn rebuilt_handlers_need_truncation_over_whole_archive() {
+        let store = Arc::new(archive());
+        for handlers in [&["death"][..], &["pos"], &[]] {
+            let router = RecordingRouter::default();
+            assert!(LogReplayer::new(store.clone(), router, handlers).is_err());
+        }
+
+        let (replayer, _) = replayer(&store, &["position", "death"]);
+        let (from, to) = (BlockNumber::new(1), BlockNumber::new(3));
+        assert!(replayer.replay(from, to).await.is_err());
+
+        let replayer = replayer.with_truncate_derived(true);
+        assert!(replayer.replay(BlockNumber::new(2), to).await.is_err());
+        assert!(store.cleared.lock().is_empty());
+
+        let report = replayer.replay(from, to).await.unwrap();
+        assert_eq!(report.rows_cleared, 3);
+        assert_eq!(*store.cleared.lock(), vec!["death", "position"]);
+        assert_eq!(report.routed, 2);
+    }
+}
@@ -44,31 +44,111 @@
 //! - Logs are visible immediately after transaction execution
 //! - Requires keep-alive pings every 30 seconds
 //!
+//! # Transaction Context
+//!
+//! With `tx_context.enabled`, both processors pass each event's metadata
+//! through a [`TxContextResolver`], which records the GHOSTNET function and
+//! sender of the emitting transaction.
+//!
+//! # Scan Prediction
+//!
+//! [`ScanPredictor`] predicts each level's next scan from `GhostCore`'s
+//! schedule, or from the cadence of recent scans. The `ScanHandler`
+//! invalidates a level's cached prediction when it is scanned.
+//!
+//! # State Verification
+//!
+//! [`StateVerifier`] compares indexed positions, rounds and stats with the
+//! contracts through batched `eth_call`s, and can overwrite what differs.
+//! [`HolderReconciler`] does the same for a sample of the DATA holder
+//! balances, every hour by default.
+//!
+//! # Replay
+//!
+//! [`LogReplayer`] routes the raw logs the [`Pipeline`] archived through the
+//! handlers again, so their tables can be rebuilt without refetching from
+//! RPC.
+//!
+//! # Quarantine
+//!
+//! With a [`Quarantine`], the [`EventRouter`] sets aside the logs a handler
+//! keeps failing on and carries on with the next log. [`QuarantineRetrier`]
+//! routes them again once the handler is fixed.
+//!
+//! # Derived Events
+//!
+//! [`RoundWatcher`] publishes events for DeadPool rounds that no log marks:
+//! a deadline coming near, and a deadline passing without a resolution.
+//! [`BoostExpirer`] does the same for boosts that lapse or whose position
+//! closes.
+//!
 //! # Usage
 //!
 //! ```ignore
-//! use ghostnet_indexer::indexer::{BlockProcessor, RealtimeProcessor, EventRouter};
+//! use ghostnet_indexer::indexer::{BlockProcessor, ContractRegistry, Pipeline, RealtimeProcessor};
+//!
+//! let contracts = Arc::new(ContractRegistry::from_config(&settings.contracts)?);
+//! contracts.verify(&http_provider).await?;
 //!
 //! // For historical backfill (HTTP)
-//! let block_processor = BlockProcessor::new(http_provider, contracts, log_tx)?;
+//! let block_processor = BlockProcessor::new(http_provider, contracts.clone(), ingest_tx, None)
+//!     .with_shutdown(shutdown.clone());
 //! block_processor.backfill(from_block, to_block).await?;
 //!
 //! // For real-time indexing (WebSocket)
-//! let realtime_processor = RealtimeProcessor::new(ws_url, contracts, log_tx)?;
-//! realtime_processor.start().await?; // Runs until shutdown
+//! let realtime_processor = RealtimeProcessor::new(ws_url, contracts, ingest_tx)?;
+//! realtime_processor.start(shutdown.clone()).await?; // Runs until shutdown
+//!
+//! // Route logs and checkpoint completed blocks until the processors stop
+//! Pipeline::new(router, checkpoints).run(ingest_rx, shutdown).await?;
 //! ```
 
 mod block_processor;
+mod boost_expirer;
 mod checkpoint;
+mod contract_registry;
+mod event_kind;
 mod event_router;
+mod holder_reconciler;
+mod leaderboard_refresher;
+mod log_replay;
+mod pipeline;
+mod quarantine;
 mod realtime_processor;
 mod reorg_handler;
+mod round_watcher;
+mod scan_predictor;
+mod state_verifier;
+mod stats_aggregator;
+mod tx_context;
+mod user_profile;
 
 pub use block_processor::BlockProcessor;
-pub use checkpoint::{CheckpointManager, CheckpointState, RecoveryMode};
-pub use event_router::EventRouter;
+pub use boost_expirer::BoostExpirer;
+pub use checkpoint::{CheckpointManager, CheckpointState, RecoveryMode, ResumePlan};
+pub use contract_registry::{
+    Contract, ContractRegistry, ContractScope, SharedRegistry, Verification,
+};
+pub use event_kind::EventKind;
+pub use event_router::{EventRouter, RouterStats};
+pub use holder_reconciler::{HolderReconciler, ReconcileReport};
+pub use leaderboard_refresher::LeaderboardRefresher;
+pub use log_replay::{LogReplayer, ReplayReport, ScopedRouter};
+pub use pipeline::{Ingest, LogRouter, Pipeline};
+pub use quarantine::{Quarantine, QuarantineRetrier, RetryOutcome, RetryReport};
 pub use realtime_processor::RealtimeProcessor;
 pub use reorg_handler::{ReorgCheckResult, ReorgHandler, ReorgStats};
+pub use round_watcher::RoundWatcher;
+pub use scan_predictor::ScanPredictor;
+pub use state_verifier::{
+    JournalEntry, MULTICALL3_ADDRESS, Mismatch, Severity, StateVerifier, Subject, VerifyReport,
+};
+pub use stats_aggregator::StatsAggregator;
+pub use tx_context::{TxContext, TxContextResolver, function_name};
+pub use user_profile::{
+    BettingSummary, CurrentPosition, PositionHistorySummary, ProfileSection, TokenFlowSummary,
+    UserProfile, UserProfileService,
+};
 
 // Re-export MegaETH RPC types from the shared crate
 pub use megaeth_rpc::{FetchStats, MegaEthClient};
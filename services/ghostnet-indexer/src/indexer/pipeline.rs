@@ -0,0 +1,1368 @@
+//! Log pipeline: routes ingested logs and checkpoints completed blocks.
+//!
+//! The [`BlockProcessor`](super::BlockProcessor) and
+//! [`RealtimeProcessor`](super::RealtimeProcessor) feed [`Ingest`] messages
+//! into a channel. The [`Pipeline`] routes each log through the event router
+//! and records the last block whose logs have all been handled in the
+//! checkpoint store.
+//!
+//! # Block Completion
+//!
+//! A block is complete once a log from a later block arrives, or once a
+//! processor reports the range containing it with [`Ingest::BlocksComplete`].
+//! The block being routed when the pipeline stops is never checkpointed, so a
+//! restart resumes from the first block that may be partially indexed.
+//!
+//! # Batched Writes
+//!
+//! With a [batch window](Pipeline::with_batch_window), the store holds the
+//! handlers' writes (see [`PostgresStore::batched`]) and the pipeline commits
+//! them together with the checkpoint, between blocks: before routing the first
+//! log of a block once the window has elapsed, and when a range completes. A
+//! zero window commits every block. Blocks are never committed in part, so a
+//! block still being routed when the pipeline stops is discarded with the rest
+//! of the batch, and a failed commit stops the pipeline.
+//!
+//! [`PostgresStore::batched`]: crate::store::PostgresStore::batched
+//!
+//! # Archive
+//!
+//! With an [archive](Pipeline::with_archive), each log is stored undecoded
+//! before it is routed, for the [`LogReplayer`](super::LogReplayer). Given the
+//! batched store, the archive commits with the handlers' writes.
+//!
+//! # Store Outages
+//!
+//! A log whose archiving or handling fails because the store cannot be
+//! reached (see [`is_unavailable`]) is not skipped. The pipeline pauses,
+//! pings the store with growing delays until it answers, and then routes
+//! again what the outage may have lost: without a batch window the log that
+//! failed, with one every log since the last commit, as the batch's
+//! transaction went down with its connection. The processors block on the
+//! full channel meanwhile, and continue where they were once it drains.
+//!
+//! # Quarantine
+//!
+//! A log the router quarantined (see [`Quarantine`](super::Quarantine)) is
+//! not routed again. With a batch window, the handler that failed may have
+//! left part of its writes in the batch, or its transaction aborted, so the
+//! pipeline discards the batch and routes every log since the last commit
+//! again, skipping the quarantined one.
+//!
+//! # Shutdown
+//!
+//! ```text
+//! shutdown token ──▶ processors stop pulling new work, finish their batch
+//!                    and drop their senders
+//!                ──▶ pipeline drains the channel (bounded by the grace period)
+//!                ──▶ final checkpoint
+//! ```
+
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use alloy::primitives::B256;
+use alloy::rpc::types::Log;
+use async_trait::async_trait;
+use tokio::sync::mpsc;
+use tokio::time::{Instant, MissedTickBehavior, interval, timeout};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, error, info, instrument, warn};
+
+use crate::error::{AppError, Result};
+use crate::handlers::{
+    DeathPort, EmissionsPort, FeePort, MarketPort, PositionPort, ScanPort, TokenPort,
+};
+use crate::obs;
+use crate::ports::{IndexerStateStore, RawLogStore};
+use crate::store::retry::is_unavailable;
+use crate::types::entities::RawLog;
+use crate::types::events::EventMetadata;
+use crate::types::primitives::BlockNumber;
+
+use super::{CheckpointManager, EventRouter};
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// CONSTANTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Default time allowed for the channel to drain after shutdown.
+const DEFAULT_GRACE_PERIOD: Duration = Duration::from_secs(30);
+
+/// Default interval between checkpoint writes.
+const DEFAULT_CHECKPOINT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Delay before pinging an unavailable store the first time.
+const STORE_PROBE_DELAY: Duration = Duration::from_millis(250);
+
+/// Upper bound of the delay between pings of an unavailable store.
+const MAX_STORE_PROBE_DELAY: Duration = Duration::from_secs(10);
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// INGEST
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Work sent from the block processors to the [`Pipeline`].
+#[derive(Debug, Clone)]
+#[allow(clippy::large_enum_variant)] // Nearly every message is a log; boxing would allocate each
+pub enum Ingest {
+    /// A log to route.
+    Log(Log, EventMetadata),
+    /// Every log up to and including `through` has been sent.
+    BlocksComplete {
+        /// Last block of the completed range.
+        through: u64,
+        /// Hash of `through`.
+        hash: B256,
+    },
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// LOG ROUTER
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Something that routes logs to handlers.
+///
+/// Implemented by [`EventRouter`]; lets the pipeline be tested without
+/// wiring all seven handler ports.
+#[async_trait]
+pub trait LogRouter: Send + Sync {
+    /// Route a single log. See [`EventRouter::route_log`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the log cannot be decoded or handled.
+    async fn route_log(&self, log: &Log, meta: EventMetadata) -> Result<bool>;
+}
+
+#[async_trait]
+impl<P, S, D, M, T, F, E> LogRouter for EventRouter<P, S, D, M, T, F, E>
+where
+    P: PositionPort,
+    S: ScanPort,
+    D: DeathPort,
+    M: MarketPort,
+    T: TokenPort,
+    F: FeePort,
+    E: EmissionsPort,
+{
+    async fn route_log(&self, log: &Log, meta: EventMetadata) -> Result<bool> {
+        Self::route_log(self, log, meta).await
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// PROGRESS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Block progress of the pipeline.
+#[derive(Debug, Default)]
+struct Progress {
+    /// Block whose logs are being routed.
+    current: Option<(u64, B256)>,
+    /// Last block whose logs have all been routed.
+    completed: Option<(u64, B256)>,
+    /// Last block written to the checkpoint store.
+    checkpointed: Option<u64>,
+    /// When the first log of the uncommitted batch was routed.
+    batch_started: Option<Instant>,
+    /// Work routed again after a store outage: everything since the last
+    /// commit with a batch window, the item being processed otherwise.
+    uncommitted: Vec<Ingest>,
+}
+
+impl Progress {
+    /// Check if a log from `block` starts a new block.
+    fn starts_block(&self, block: u64) -> bool {
+        self.current.is_none_or(|(current, _)| block > current)
+    }
+
+    /// Record that a log from `block` is about to be routed.
+    fn start_log(&mut self, block: u64, hash: B256) {
+        if let Some((current, current_hash)) = self.current
+            && block > current
+        {
+            self.complete(current, current_hash);
+        }
+        self.current = Some((block, hash));
+    }
+
+    /// Record that every block up to `through` is complete.
+    fn complete_through(&mut self, through: u64, hash: B256) {
+        if self.current.is_some_and(|(current, _)| current <= through) {
+            self.current = None;
+        }
+        self.complete(through, hash);
+    }
+
+    fn complete(&mut self, block: u64, hash: B256) {
+        if self
+            .completed
+            .is_none_or(|(completed, _)| block > completed)
+        {
+            self.completed = Some((block, hash));
+        }
+    }
+
+    /// Forget the blocks of a batch the store lost, to route them again.
+    const fn rewind(&mut self) {
+        self.current = None;
+        self.completed = None;
+        self.batch_started = None;
+    }
+
+    /// Completed block not yet written to the checkpoint store.
+    fn pending_checkpoint(&self) -> Option<(u64, B256)> {
+        self.completed
+            .filter(|(block, _)| self.checkpointed.is_none_or(|saved| *block > saved))
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// PIPELINE
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Routes ingested logs and checkpoints completed blocks.
+///
+/// # Type Parameters
+///
+/// * `R` - Log router (usually an [`EventRouter`])
+/// * `S` - Store implementing [`IndexerStateStore`]
+pub struct Pipeline<R, S> {
+    /// Router for decoded logs.
+    router: R,
+    /// Checkpoint persistence.
+    checkpoints: CheckpointManager<S>,
+    /// Time allowed for the channel to drain after shutdown.
+    grace_period: Duration,
+    /// Interval between checkpoint writes.
+    checkpoint_interval: Duration,
+    /// Minimum time between batch commits, if the store batches writes.
+    batch_window: Option<Duration>,
+    /// Store archiving each log before it is routed.
+    archive: Option<Arc<dyn RawLogStore>>,
+}
+
+impl<R: fmt::Debug, S: fmt::Debug> fmt::Debug for Pipeline<R, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Pipeline")
+            .field("router", &self.router)
+            .field("checkpoints", &self.checkpoints)
+            .field("grace_period", &self.grace_period)
+            .field("checkpoint_interval", &self.checkpoint_interval)
+            .field("batch_window", &self.batch_window)
+            .field("archive", &self.archive.is_some())
+            .finish()
+    }
+}
+
+impl<R, S> Pipeline<R, S>
+where
+    R: LogRouter,
+    S: IndexerStateStore,
+{
+    /// Create a new pipeline.
+    pub const fn new(router: R, checkpoints: CheckpointManager<S>) -> Self {
+        Self {
+            router,
+            checkpoints,
+            grace_period: DEFAULT_GRACE_PERIOD,
+            checkpoint_interval: DEFAULT_CHECKPOINT_INTERVAL,
+            batch_window: None,
+            archive: None,
+        }
+    }
+
+    /// Set the time allowed for in-flight work to drain after shutdown.
+    #[must_use]
+    pub const fn with_grace_period(mut self, grace_period: Duration) -> Self {
+        self.grace_period = grace_period;
+        self
+    }
+
+    /// Set the interval between checkpoint writes.
+    #[must_use]
+    pub const fn with_checkpoint_interval(mut self, interval: Duration) -> Self {
+        self.checkpoint_interval = interval;
+        self
+    }
+
+    /// Commit the store's batched writes with the checkpoint, at most once
+    /// per `window`; see [Batched Writes](self#batched-writes).
+    ///
+    /// Required for stores that batch writes, which otherwise never commit;
+    /// `None` is for stores that write through.
+    #[must_use]
+    pub const fn with_batch_window(mut self, window: Option<Duration>) -> Self {
+        self.batch_window = window;
+        self
+    }
+
+    /// Archive each log in `store` before routing it; see
+    /// [Archive](self#archive). `None` archives nothing.
+    ///
+    /// Pass the store the handlers write to, so the archive commits with
+    /// their writes.
+    #[must_use]
+    pub fn with_archive(mut self, store: Option<Arc<dyn RawLogStore>>) -> Self {
+        self.archive = store;
+        self
+    }
+
+    /// Route logs until the channel closes or shutdown completes.
+    ///
+    /// Once `shutdown` is cancelled, the pipeline keeps routing whatever the
+    /// processors still send until they drop their senders or the grace
+    /// period elapses, whichever comes first. In-flight handler calls are
+    /// abandoned only when the grace period runs out. A final checkpoint is
+    /// written before returning.
+    ///
+    /// # Returns
+    ///
+    /// The last checkpointed block, if any block was completed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the final checkpoint cannot be written, if a
+    /// batch fails to commit, or if the store is unavailable once shutdown
+    /// is requested.
+    #[instrument(skip_all)]
+    pub async fn run(
+        &self,
+        mut ingest: mpsc::Receiver<Ingest>,
+        shutdown: CancellationToken,
+    ) -> Result<Option<BlockNumber>> {
+        let mut progress = Progress::default();
+        let mut ticker = interval(self.checkpoint_interval);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+        let closed = loop {
+            tokio::select! {
+                // Shutdown wins over queued logs so the drain window is bounded
+                biased;
+                () = shutdown.cancelled() => break false,
+                _ = ticker.tick() => {
+                    let result = self.tick(&mut progress).await;
+                    self.recover_from(result, &mut progress, &shutdown).await?;
+                }
+                item = ingest.recv() => match item {
+                    Some(item) => {
+                        let result = self.process(item, &mut progress).await;
+                        self.recover_from(result, &mut progress, &shutdown).await?;
+                    }
+                    None => break true,
+                },
+            }
+        };
+
+        if !closed {
+            info!(grace_period = ?self.grace_period, "Shutdown requested, draining in-flight logs");
+            let drain = async {
+                while let Some(item) = ingest.recv().await {
+                    match self.process(item, &mut progress).await {
+                        Err(AppError::Quarantined(id)) => self.reroute(id, &mut progress).await?,
+                        result => result?,
+                    }
+                }
+                Result::Ok(())
+            };
+            if let Ok(drained) = timeout(self.grace_period, drain).await {
+                drained?;
+            } else {
+                warn!(
+                    block = ?progress.current.map(|(block, _)| block),
+                    "Grace period elapsed, aborting in-flight logs"
+                );
+            }
+        }
+
+        match (self.batch_window, progress.current) {
+            (Some(_), Some((block, _))) => {
+                warn!(block, "Discarding batched writes, block was not fully routed");
+                self.checkpoints.store().discard_batch().await?;
+            }
+            (Some(_), None) => self.commit(&mut progress).await?,
+            (None, _) => self.checkpoint(&mut progress).await?,
+        }
+        info!(checkpoint = ?progress.checkpointed, "Pipeline stopped");
+        Ok(progress.checkpointed.map(BlockNumber::new))
+    }
+
+    /// Route a log or record a completed range, committing the batch in
+    /// between blocks once due.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the batch fails to commit, if the store is
+    /// unavailable, or if a log was quarantined into a batch.
+    async fn process(&self, item: Ingest, progress: &mut Progress) -> Result<()> {
+        if self.batch_window.is_none() {
+            progress.uncommitted.clear();
+        }
+        match item {
+            Ingest::Log(log, meta) => {
+                let starts_block = progress.starts_block(meta.block_number);
+                progress.start_log(meta.block_number, meta.block_hash);
+                if starts_block && self.batch_due(progress) {
+                    self.commit(progress).await?;
+                }
+                if self.batch_window.is_some() {
+                    progress.batch_started.get_or_insert_with(Instant::now);
+                }
+                progress.uncommitted.push(Ingest::Log(log.clone(), meta.clone()));
+
+                let (block, tx_hash) = (meta.block_number, meta.tx_hash);
+                if let Some(archive) = &self.archive
+                    && let Err(e) = archive.archive_logs(&[RawLog::new(&log, &meta)]).await
+                {
+                    if is_unavailable(&e) {
+                        return Err(e);
+                    }
+                    error!(block, tx_hash = %tx_hash, error = %e, "Failed to archive log");
+                }
+                // Handler failures are logged and skipped, like decode
+                // failures, unless the store is down
+                match self.router.route_log(&log, meta).await {
+                    Err(e) if is_unavailable(&e) => return Err(e),
+                    // The batch may hold part of the quarantined log's writes
+                    Err(e @ AppError::Quarantined(_)) if self.batch_window.is_some() => {
+                        return Err(e);
+                    }
+                    // Logged when it was quarantined
+                    Err(AppError::Quarantined(_)) | Ok(_) => {}
+                    Err(e) => {
+                        error!(block, tx_hash = %tx_hash, error = %e, "Failed to route log");
+                    }
+                }
+            }
+            Ingest::BlocksComplete { through, hash } => {
+                debug!(through, "Blocks complete");
+                progress.uncommitted.push(Ingest::BlocksComplete { through, hash });
+                progress.complete_through(through, hash);
+                if progress.current.is_none() && self.batch_due(progress) {
+                    self.commit(progress).await?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Pass on `result`, unless it failed because the store is unavailable:
+    /// then [recover](Self::recover) instead, or because a log was
+    /// quarantined into the batch: then [reroute](Self::reroute). An outage
+    /// is passed on once shutdown is requested.
+    async fn recover_from(
+        &self,
+        mut result: Result<()>,
+        progress: &mut Progress,
+        shutdown: &CancellationToken,
+    ) -> Result<()> {
+        loop {
+            result = match result {
+                Err(e) if is_unavailable(&e) && !shutdown.is_cancelled() => {
+                    self.recover(e, progress, shutdown).await
+                }
+                Err(AppError::Quarantined(id)) => self.reroute(id, progress).await,
+                result => return result,
+            };
+        }
+    }
+
+    /// Discard the batch a log was quarantined into and route everything
+    /// since the last commit again; see [Quarantine](self#quarantine).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the batch cannot be discarded, and any error of
+    /// routing again other than another quarantined log.
+    async fn reroute(&self, mut id: i64, progress: &mut Progress) -> Result<()> {
+        loop {
+            debug!(id, "Log quarantined, routing the batch again without it");
+            self.checkpoints.store().discard_batch().await?;
+            progress.rewind();
+            match self.replay(progress).await {
+                Err(AppError::Quarantined(next)) => id = next,
+                result => return result,
+            }
+        }
+    }
+
+    /// Wait for the store to answer again, then route again what the outage
+    /// may have lost; see [Store Outages](self#store-outages).
+    ///
+    /// # Errors
+    ///
+    /// Returns `error` if shutdown is requested before the store returns,
+    /// and any error of routing again other than another outage.
+    async fn recover(
+        &self,
+        mut error: AppError,
+        progress: &mut Progress,
+        shutdown: &CancellationToken,
+    ) -> Result<()> {
+        let started = Instant::now();
+        obs::set_store_degraded(true);
+        warn!(error = %error, "Store unavailable, pausing ingestion");
+
+        let recovered = loop {
+            if self.batch_window.is_some() {
+                // Whatever the batch held went down with its connection
+                if let Err(e) = self.checkpoints.store().discard_batch().await {
+                    debug!(error = %e, "Failed to roll back lost batch");
+                }
+                progress.rewind();
+            }
+            if !self.wait_for_store(shutdown).await {
+                break Err(error);
+            }
+            match self.replay(progress).await {
+                Err(e) if is_unavailable(&e) => {
+                    warn!(error = %e, "Store unavailable again");
+                    error = e;
+                }
+                result => break result,
+            }
+        };
+
+        obs::set_store_degraded(false);
+        obs::record_store_outage(started.elapsed());
+        if recovered.is_ok() {
+            info!(outage = ?started.elapsed(), "Store available, ingestion resumed");
+        }
+        recovered
+    }
+
+    /// Ping the store with growing delays until it answers.
+    ///
+    /// Returns `false` if shutdown is requested first.
+    async fn wait_for_store(&self, shutdown: &CancellationToken) -> bool {
+        let mut delay = STORE_PROBE_DELAY;
+        loop {
+            tokio::select! {
+                () = shutdown.cancelled() => return false,
+                () = tokio::time::sleep(delay) => {}
+            }
+            match self.checkpoints.store().ping().await {
+                Ok(()) => return true,
+                Err(e) => debug!(error = %e, retry_in = ?delay, "Store still unavailable"),
+            }
+            delay = (delay * 2).min(MAX_STORE_PROBE_DELAY);
+        }
+    }
+
+    /// Process the uncommitted work again.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error of [`process`](Self::process), keeping the
+    /// work not yet processed again for the next attempt.
+    async fn replay(&self, progress: &mut Progress) -> Result<()> {
+        let mut items = std::mem::take(&mut progress.uncommitted).into_iter();
+        debug!(items = items.len(), "Routing uncommitted work again");
+        while let Some(item) = items.next() {
+            if let Err(e) = self.process(item, progress).await {
+                progress.uncommitted.extend(items);
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+
+    /// Checkpoint on the ticker, committing the batch only in between blocks.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the batch fails to commit. Checkpoint failures
+    /// without batching are retried on the next tick.
+    async fn tick(&self, progress: &mut Progress) -> Result<()> {
+        if self.batch_window.is_none() {
+            if let Err(e) = self.checkpoint(progress).await {
+                error!(error = %e, "Failed to write checkpoint, will retry");
+            }
+        } else if progress.current.is_none() && self.batch_due(progress) {
+            self.commit(progress).await?;
+        }
+        Ok(())
+    }
+
+    /// Check if the batch window has elapsed since the batch's first log.
+    fn batch_due(&self, progress: &Progress) -> bool {
+        self.batch_window.is_some_and(|window| {
+            progress
+                .batch_started
+                .is_none_or(|started| started.elapsed() >= window)
+        })
+    }
+
+    /// Write the checkpoint into the batch and commit it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the checkpoint cannot be written or the batch
+    /// fails to commit, in which case the batch is rolled back.
+    async fn commit(&self, progress: &mut Progress) -> Result<()> {
+        let pending = progress.pending_checkpoint();
+        if pending.is_none() && progress.batch_started.is_none() {
+            return Ok(());
+        }
+
+        let store = self.checkpoints.store();
+        if let Some((block, hash)) = pending
+            && let Err(e) = self.checkpoints.update(BlockNumber::new(block), hash).await
+        {
+            store.discard_batch().await?;
+            return Err(e);
+        }
+        store.commit_batch().await?;
+
+        if let Some((block, _)) = pending {
+            progress.checkpointed = Some(block);
+        }
+        progress.batch_started = None;
+        progress.uncommitted.clear();
+        debug!(checkpoint = ?progress.checkpointed, "Batch committed");
+        Ok(())
+    }
+
+    /// Write the last completed block to the checkpoint store, if it moved.
+    async fn checkpoint(&self, progress: &mut Progress) -> Result<()> {
+        if let Some((block, hash)) = progress.pending_checkpoint() {
+            self.checkpoints
+                .update(BlockNumber::new(block), hash)
+                .await?;
+            progress.checkpointed = Some(block);
+        }
+        Ok(())
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::significant_drop_tightening)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    use alloy::primitives::{Address, Bytes, LogData};
+    use alloy::providers::ProviderBuilder;
+    use alloy::rpc::types::{Block, Transaction};
+    use alloy::sol_types::SolEvent;
+    use alloy::transports::mock::Asserter;
+    use chrono::Utc;
+    use parking_lot::Mutex;
+
+    use super::*;
+    use crate::abi::dead_pool;
+    use crate::config::ContractAddresses;
+    use crate::error::InfraError;
+    use crate::indexer::{BlockProcessor, Contract, ContractRegistry, ContractScope};
+
+    /// Router that takes `delay` per log and records the blocks it finished.
+    #[derive(Debug, Clone)]
+    struct SlowRouter {
+        delay: Duration,
+        routed: Arc<Mutex<Vec<u64>>>,
+    }
+
+    #[async_trait]
+    impl LogRouter for SlowRouter {
+        async fn route_log(&self, _log: &Log, meta: EventMetadata) -> Result<bool> {
+            tokio::time::sleep(self.delay).await;
+            self.routed.lock().push(meta.block_number);
+            Ok(true)
+        }
+    }
+
+    /// Router whose first attempt at each block of `outages` fails as if the
+    /// store went away.
+    #[derive(Debug, Default, Clone)]
+    struct OutageRouter {
+        outages: Arc<Mutex<Vec<u64>>>,
+        routed: Arc<Mutex<Vec<u64>>>,
+    }
+
+    impl OutageRouter {
+        fn failing_at(blocks: &[u64]) -> Self {
+            Self {
+                outages: Arc::new(Mutex::new(blocks.to_vec())),
+                routed: Arc::default(),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl LogRouter for OutageRouter {
+        async fn route_log(&self, _log: &Log, meta: EventMetadata) -> Result<bool> {
+            let mut outages = self.outages.lock();
+            if let Some(index) = outages.iter().position(|block| *block == meta.block_number) {
+                outages.remove(index);
+                return Err(InfraError::Database(sqlx::Error::PoolTimedOut).into());
+            }
+            self.routed.lock().push(meta.block_number);
+            Ok(true)
+        }
+    }
+
+    /// Router that quarantines the logs of `poisoned`: the first attempt at
+    /// each fails with [`AppError::Quarantined`], later ones skip it.
+    #[derive(Debug, Default, Clone)]
+    struct PoisonRouter {
+        poisoned: Vec<u64>,
+        quarantined: Arc<Mutex<Vec<u64>>>,
+        routed: Arc<Mutex<Vec<u64>>>,
+    }
+
+    #[async_trait]
+    impl LogRouter for PoisonRouter {
+        async fn route_log(&self, _log: &Log, meta: EventMetadata) -> Result<bool> {
+            let block = meta.block_number;
+            let mut quarantined = self.quarantined.lock();
+            if quarantined.contains(&block) {
+                return Ok(false);
+            }
+            if self.poisoned.contains(&block) {
+                quarantined.push(block);
+                return Err(AppError::Quarantined(1));
+            }
+            self.routed.lock().push(block);
+            Ok(true)
+        }
+    }
+
+    /// State store that only keeps the checkpoint and cursors.
+    ///
+    /// With `batched`, the checkpoint is staged until the batch commits, and
+    /// every commit is recorded. The first `failed_pings` pings fail.
+    #[derive(Debug, Default, Clone)]
+    struct CheckpointStore {
+        last: Arc<Mutex<Option<(u64, B256)>>>,
+        cursors: Arc<Mutex<HashMap<Contract, u64>>>,
+        batched: bool,
+        staged: Arc<Mutex<Option<(u64, B256)>>>,
+        commits: Arc<Mutex<Vec<Option<u64>>>>,
+        discards: Arc<Mutex<u32>>,
+        fail_commit: bool,
+        failed_pings: Arc<Mutex<u32>>,
+    }
+
+    impl CheckpointStore {
+        fn batched() -> Self {
+            Self {
+                batched: true,
+                ..Self::default()
+            }
+        }
+    }
+
+    #[async_trait]
+    impl IndexerStateStore for CheckpointStore {
+        async fn get_last_block(&self) -> Result<BlockNumber> {
+            Ok(BlockNumber::new(
+                self.last.lock().map_or(0, |(block, _)| block),
+            ))
+        }
+
+        async fn set_last_block(&self, block: BlockNumber, hash: B256) -> Result<()> {
+            let target = if self.batched { &self.staged } else { &self.last };
+            *target.lock() = Some((block.value(), hash));
+            Ok(())
+        }
+
+        async fn insert_block_hash(
+            &self,
+            _block: BlockNumber,
+            _hash: B256,
+            _parent: B256,
+            _timestamp: u64,
+        ) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_block_hash(&self, _block: BlockNumber) -> Result<Option<B256>> {
+            Ok(None)
+        }
+
+        async fn execute_reorg_rollback(&self, _fork_point: BlockNumber) -> Result<()> {
+            Ok(())
+        }
+
+        async fn prune_old_blocks(&self, _keep_blocks: u64) -> Result<u64> {
+            Ok(0)
+        }
+
+        async fn get_cursor(&self, contract: Contract) -> Result<Option<BlockNumber>> {
+            Ok(self
+                .cursors
+                .lock()
+                .get(&contract)
+                .copied()
+                .map(BlockNumber::new))
+        }
+
+        async fn set_cursor(&self, contract: Contract, block: BlockNumber) -> Result<()> {
+            self.cursors.lock().insert(contract, block.value());
+            Ok(())
+        }
+
+        async fn min_cursor(&self, contracts: &[Contract]) -> Result<Option<BlockNumber>> {
+            let cursors = self.cursors.lock();
+            Ok(contracts
+                .iter()
+                .map(|contract| cursors.get(contract).copied().unwrap_or(0))
+                .min()
+                .map(BlockNumber::new))
+        }
+
+        async fn commit_batch(&self) -> Result<()> {
+            let staged = self.staged.lock().take();
+            if self.fail_commit {
+                return Err(InfraError::Internal("commit failed".into()).into());
+            }
+            if staged.is_some() {
+                *self.last.lock() = staged;
+            }
+            self.commits.lock().push(self.last.lock().map(|(block, _)| block));
+            Ok(())
+        }
+
+        async fn discard_batch(&self) -> Result<()> {
+            *self.staged.lock() = None;
+            *self.discards.lock() += 1;
+            Ok(())
+        }
+
+        async fn ping(&self) -> Result<()> {
+            let mut failed = self.failed_pings.lock();
+            if *failed > 0 {
+                *failed -= 1;
+                return Err(InfraError::Database(sqlx::Error::PoolTimedOut).into());
+            }
+            Ok(())
+        }
+    }
+
+    /// Archive that only records the logs archived.
+    #[derive(Debug, Default, Clone)]
+    struct Archive {
+        archived: Arc<Mutex<Vec<RawLog>>>,
+    }
+
+    #[async_trait]
+    impl RawLogStore for Archive {
+        async fn archive_logs(&self, logs: &[RawLog]) -> Result<()> {
+            self.archived.lock().extend_from_slice(logs);
+            Ok(())
+        }
+
+        async fn get_raw_logs(
+            &self,
+            _from: BlockNumber,
+            _to: BlockNumber,
+            _after: Option<(BlockNumber, u64, u64)>,
+            _limit: u32,
+        ) -> Result<Vec<RawLog>> {
+            Ok(vec![])
+        }
+
+        async fn raw_log_range(&self) -> Result<Option<(BlockNumber, BlockNumber)>> {
+            Ok(None)
+        }
+
+        async fn get_replay_progress(&self, _replay: &str) -> Result<Option<BlockNumber>> {
+            Ok(None)
+        }
+
+        async fn set_replay_progress(&self, _replay: &str, _block: BlockNumber) -> Result<()> {
+            Ok(())
+        }
+
+        async fn clear_replay_progress(&self, _replay: &str) -> Result<()> {
+            Ok(())
+        }
+
+        async fn clear_derived(&self, _handlers: &[&str]) -> Result<u64> {
+            Ok(0)
+        }
+    }
+
+    fn log_at(block: u64) -> Ingest {
+        let meta = EventMetadata {
+            block_number: block,
+            block_hash: hash_of(block),
+            tx_hash: B256::ZERO,
+            tx_index: 0,
+            log_index: 0,
+            timestamp: Utc::now(),
+            contract: Address::ZERO,
+            chain_id: 6343,
+            tx_function: None,
+            tx_from: None,
+        };
+        Ingest::Log(Log::default(), meta)
+    }
+
+    fn hash_of(block: u64) -> B256 {
+        B256::left_padding_from(&block.to_be_bytes())
+    }
+
+    fn pipeline(
+        delay: Duration,
+        grace: Duration,
+    ) -> (
+        Pipeline<SlowRouter, CheckpointStore>,
+        SlowRouter,
+        CheckpointStore,
+    ) {
+        let router = SlowRouter {
+            delay,
+            routed: Arc::default(),
+        };
+        let store = CheckpointStore::default();
+        let pipeline = Pipeline::new(router.clone(), CheckpointManager::new(store.clone()))
+            .with_grace_period(grace);
+        (pipeline, router, store)
+    }
+
+    #[test]
+    fn progress_completes_blocks_in_order() {
+        let mut progress = Progress::default();
+        progress.start_log(10, hash_of(10));
+        progress.start_log(10, hash_of(10));
+        assert_eq!(progress.pending_checkpoint(), None);
+
+        progress.start_log(12, hash_of(12));
+        assert_eq!(progress.pending_checkpoint(), Some((10, hash_of(10))));
+
+        progress.complete_through(15, hash_of(15));
+        assert_eq!(progress.current, None);
+        assert_eq!(progress.pending_checkpoint(), Some((15, hash_of(15))));
+
+        progress.checkpointed = Some(15);
+        progress.complete_through(14, hash_of(14));
+        assert_eq!(progress.pending_checkpoint(), None);
+    }
+
+    #[tokio::test]
+    async fn closed_channel_checkpoints_reported_ranges() {
+        let (pipeline, router, store) = pipeline(Duration::ZERO, Duration::from_secs(1));
+        let (tx, rx) = mpsc::channel(16);
+
+        tx.send(log_at(5)).await.unwrap();
+        tx.send(log_at(7)).await.unwrap();
+        tx.send(Ingest::BlocksComplete {
+            through: 9,
+            hash: hash_of(9),
+        })
+        .await
+        .unwrap();
+        drop(tx);
+
+        let last = pipeline.run(rx, CancellationToken::new()).await.unwrap();
+        assert_eq!(last, Some(BlockNumber::new(9)));
+        assert_eq!(*store.last.lock(), Some((9, hash_of(9))));
+        assert_eq!(*router.routed.lock(), vec![5, 7]);
+    }
+
+    #[tokio::test]
+    async fn shutdown_drains_in_flight_batch() {
+        let (pipeline, router, store) = pipeline(Duration::from_millis(20), Duration::from_secs(5));
+        let (tx, rx) = mpsc::channel(16);
+        let shutdown = CancellationToken::new();
+
+        // A processor that finishes its batch after shutdown is requested
+        let producer = {
+            let shutdown = shutdown.clone();
+            tokio::spawn(async move {
+                for block in [1, 1, 2, 3] {
+                    tx.send(log_at(block)).await.unwrap();
+                }
+                shutdown.cancel();
+                tx.send(log_at(3)).await.unwrap();
+                tx.send(Ingest::BlocksComplete {
+                    through: 3,
+                    hash: hash_of(3),
+                })
+                .await
+                .unwrap();
+            })
+        };
+
+        let last = pipeline.run(rx, shutdown).await.unwrap();
+        producer.await.unwrap();
+
+        assert_eq!(last, Some(BlockNumber::new(3)));
+        assert_eq!(*store.last.lock(), Some((3, hash_of(3))));
+        assert_eq!(*router.routed.lock(), vec![1, 1, 2, 3, 3]);
+    }
+
+    #[tokio::test]
+    async fn grace_period_abort_keeps_last_complete_block() {
+        let (pipeline, router, store) =
+            pipeline(Duration::from_millis(200), Duration::from_millis(300));
+        let (tx, rx) = mpsc::channel(16);
+        let shutdown = CancellationToken::new();
+
+        for block in [1, 2, 2, 2] {
+            tx.send(log_at(block)).await.unwrap();
+        }
+        shutdown.cancel();
+
+        // The sender is never dropped, so only the grace period ends the drain
+        let last = pipeline.run(rx, shutdown).await.unwrap();
+        drop(tx);
+
+        // Block 2 was still being routed when the grace period elapsed
+        assert_eq!(last, Some(BlockNumber::new(1)));
+        assert_eq!(*store.last.lock(), Some((1, hash_of(1))));
+        assert!(router.routed.lock().len() < 4);
+    }
+
+    fn batched_pipeline(
+        delay: Duration,
+        window: Duration,
+        store: CheckpointStore,
+    ) -> Pipeline<SlowRouter, CheckpointStore> {
+        let router = SlowRouter {
+            delay,
+            routed: Arc::default(),
+        };
+        Pipeline::new(router, CheckpointManager::new(store)).with_batch_window(Some(window))
+    }
+
+    #[tokio::test]
+    async fn batches_commit_with_checkpoint_between_blocks() {
+        let store = CheckpointStore::batched();
+        let pipeline = batched_pipeline(Duration::ZERO, Duration::ZERO, store.clone());
+        let (tx, rx) = mpsc::channel(16);
+
+        for block in [1, 1, 2, 3] {
+            tx.send(log_at(block)).await.unwrap();
+        }
+        tx.send(Ingest::BlocksComplete {
+            through: 3,
+            hash: hash_of(3),
+        })
+        .await
+        .unwrap();
+        drop(tx);
+
+        let last = pipeline.run(rx, CancellationToken::new()).await.unwrap();
+        assert_eq!(last, Some(BlockNumber::new(3)));
+        assert_eq!(*store.commits.lock(), vec![Some(1), Some(2), Some(3)]);
+        assert_eq!(*store.last.lock(), Some((3, hash_of(3))));
+    }
+
+    #[tokio::test]
+    async fn batch_window_spans_blocks() {
+        let store = CheckpointStore::batched();
+        let pipeline = batched_pipeline(Duration::ZERO, Duration::from_secs(3600), store.clone());
+        let (tx, rx) = mpsc::channel(16);
+
+        for block in [1, 2, 3] {
+            tx.send(log_at(block)).await.unwrap();
+        }
+        tx.send(Ingest::BlocksComplete {
+            through: 3,
+            hash: hash_of(3),
+        })
+        .await
+        .unwrap();
+        drop(tx);
+
+        pipeline.run(rx, CancellationToken::new()).await.unwrap();
+        assert_eq!(*store.commits.lock(), vec![Some(3)]);
+    }
+
+    #[tokio::test]
+    async fn failed_commit_stops_pipeline() {
+        let store = CheckpointStore {
+            fail_commit: true,
+            ..CheckpointStore::batched()
+        };
+        let pipeline = batched_pipeline(Duration::ZERO, Duration::ZERO, store.clone());
+        let (tx, rx) = mpsc::channel(16);
+
+        for block in [1, 2, 3] {
+            tx.send(log_at(block)).await.unwrap();
+        }
+
+        assert!(pipeline.run(rx, CancellationToken::new()).await.is_err());
+        assert_eq!(*store.last.lock(), None);
+        drop(tx);
+    }
+
+    #[tokio::test]
+    async fn grace_period_abort_discards_batch() {
+        let store = CheckpointStore::batched();
+        let pipeline = batched_pipeline(
+            Duration::from_millis(200),
+            Duration::from_secs(3600),
+            store.clone(),
+        )
+        .with_grace_period(Duration::from_millis(300));
+        let (tx, rx) = mpsc::channel(16);
+        let shutdown = CancellationToken::new();
+
+        for block in [1, 2, 2, 2] {
+            tx.send(log_at(block)).await.unwrap();
+        }
+        shutdown.cancel();
+
+        // Block 1 is complete but shares the batch with the partial block 2
+        let last = pipeline.run(rx, shutdown).await.unwrap();
+        drop(tx);
+
+        assert_eq!(last, None);
+        assert_eq!(*store.last.lock(), None);
+        assert_eq!(*store.discards.lock(), 1);
+    }
+
+    #[tokio::test]
+    async fn added_contract_catches_up_on_its_own() {
+        // Every contract but DeadPool is indexed through block 200
+        let store = CheckpointStore::default();
+        store
+            .set_last_block(BlockNumber::new(200), hash_of(200))
+            .await
+            .unwrap();
+        for contract in Contract::ALL
+            .into_iter()
+            .filter(|c| *c != Contract::DeadPool)
+        {
+            store
+                .set_cursor(contract, BlockNumber::new(200))
+                .await
+                .unwrap();
+        }
+
+        // DeadPool was deployed at block 101 and emitted one event since
+        let dead_pool_address = Address::with_last_byte(3);
+        let bet = Log {
+            inner: alloy::primitives::Log {
+                address: dead_pool_address,
+                data: LogData::new_unchecked(
+                    vec![dead_pool::BetPlaced::SIGNATURE_HASH],
+                    Bytes::new(),
+                ),
+            },
+            block_number: Some(120),
+            block_hash: Some(hash_of(120)),
+            transaction_hash: Some(B256::repeat_byte(1)),
+            transaction_index: Some(0),
+            log_index: Some(0),
+            ..Log::default()
+        };
+        let asserter = Asserter::new();
+        asserter.push_success(&vec![bet]);
+        asserter.push_success(&Block::<Transaction>::default());
+        asserter.push_success(&Block::<Transaction>::default());
+
+        let addresses = ContractAddresses {
+            ghost_core: "0x0000000000000000000000000000000000000001".into(),
+            trace_scan: "0x0000000000000000000000000000000000000002".into(),
+            dead_pool: dead_pool_address.to_string(),
+            data_token: "0x0000000000000000000000000000000000000004".into(),
+            fee_router: "0x0000000000000000000000000000000000000005".into(),
+            rewards_distributor: "0x0000000000000000000000000000000000000006".into(),
+            disabled: vec![],
+            code_hashes: HashMap::new(),
+            additional: HashMap::new(),
+        };
+        let registry = ContractRegistry::from_config(&addresses).unwrap();
+        let provider = ProviderBuilder::new().connect_mocked_client(asserter.clone());
+        let (tx, rx) = mpsc::channel(16);
+        let processor = BlockProcessor::new(Arc::new(provider), registry, tx, None)
+            .with_scope(ContractScope::Only(Contract::DeadPool));
+        let backfill = tokio::spawn(async move { processor.backfill(101, 150).await });
+
+        let router = SlowRouter {
+            delay: Duration::ZERO,
+            routed: Arc::default(),
+        };
+        let checkpoints = CheckpointManager::for_contracts(store.clone(), [Contract::DeadPool]);
+        let pipeline = Pipeline::new(router.clone(), checkpoints);
+        let last = pipeline.run(rx, CancellationToken::new()).await.unwrap();
+        backfill.await.unwrap().unwrap();
+
+        // Only DeadPool's logs were fetched, and only its cursor moved
+        assert_eq!(last, Some(BlockNumber::new(150)));
+        assert!(asserter.read_q().is_empty());
+        assert_eq!(*router.routed.lock(), vec![120]);
+        assert_eq!(store.cursors.lock().get(&Contract::DeadPool), Some(&150));
+        assert_eq!(*store.last.lock(), Some((200, hash_of(200))));
+        for contract in Contract::ALL
+            .into_iter()
+            .filter(|c| *c != Contract::DeadPool)
+        {
+            assert_eq!(store.cursors.lock().get(&contract), Some(&200));
+        }
+    }
+
+    #[tokio::test]
+    async fn archives_logs_before_routing() {
+        let (pipeline, router, _store) = pipeline(Duration::ZERO, Duration::from_secs(1));
+        let archive = Archive::default();
+        let pipeline = pipeline.with_archive(Some(Arc::new(archive.clone())));
+        let (tx, rx) = mpsc::channel(16);
+
+        for block in [4, 4, 6] {
+            tx.send(log_at(block)).await.unwrap();
+        }
+        drop(tx);
+        pipeline.run(rx, CancellationToken::new()).await.unwrap();
+
+        let archived: Vec<_> = archive
+            .archived
+            .lock()
+            .iter()
+            .map(|log| (log.block_number.value(), log.block_hash))
+            .collect();
+        assert_eq!(archived, vec![(4, hash_of(4)), (4, hash_of(4)), (6, hash_of(6))]);
+        assert_eq!(*router.routed.lock(), vec![4, 4, 6]);
+    }
+
+    #[tokio::test]
+    async fn store_outage_routes_failed_log_again() {
+        let router = OutageRouter::failing_at(&[2]);
+        let store = CheckpointStore {
+            failed_pings: Arc::new(Mutex::new(1)),
+            ..CheckpointStore::default()
+        };
+        let pipeline = Pipeline::new(router.clone(), CheckpointManager::new(store.clone()));
+        let (tx, rx) = mpsc::channel(16);
+
+        for block in [1, 2, 3] {
+            tx.send(log_at(block)).await.unwrap();
+        }
+        tx.send(Ingest::BlocksComplete {
+            through: 3,
+            hash: hash_of(3),
+        })
+        .await
+        .unwrap();
+        drop(tx);
+
+        // Block 2 is paused on and routed once the store answers, not skipped
+        let last = pipeline.run(rx, CancellationToken::new()).await.unwrap();
+        assert_eq!(last, Some(BlockNumber::new(3)));
+        assert_eq!(*router.routed.lock(), vec![1, 2, 3]);
+        assert_eq!(*store.failed_pings.lock(), 0);
+    }
+
+    #[tokio::test]
+    async fn store_outage_routes_lost_batch_again() {
+        let router = OutageRouter::failing_at(&[3]);
+        let store = CheckpointStore::batched();
+        let checkpoints = CheckpointManager::new(store.clone());
+        let pipeline = Pipeline::new(router.clone(), checkpoints)
+            .with_batch_window(Some(Duration::from_secs(3600)));
+        let (tx, rx) = mpsc::channel(16);
+
+        for block in [1, 2, 3] {
+            tx.send(log_at(block)).await.unwrap();
+        }
+        tx.send(Ingest::BlocksComplete {
+            through: 3,
+            hash: hash_of(3),
+        })
+        .await
+        .unwrap();
+        drop(tx);
+
+        // The batch holding blocks 1 and 2 was lost with the connection
+        let last = pipeline.run(rx, CancellationToken::new()).await.unwrap();
+        assert_eq!(last, Some(BlockNumber::new(3)));
+        assert_eq!(*router.routed.lock(), vec![1, 2, 1, 2, 3]);
+        assert_eq!(*store.discards.lock(), 1);
+        assert_eq!(*store.commits.lock(), vec![Some(3)]);
+    }
+
+    #[tokio::test]
+    async fn quarantined_log_reroutes_batch_without_it() {
+        let router = PoisonRouter {
+            poisoned: vec![2],
+            ..PoisonRouter::default()
+        };
+        let store = CheckpointStore::batched();
+        let checkpoints = CheckpointManager::new(store.clone());
+        let pipeline = Pipeline::new(router.clone(), checkpoints)
+            .with_batch_window(Some(Duration::from_secs(3600)));
+        let (tx, rx) = mpsc::channel(16);
+
+        for block in [1, 2, 3] {
+            tx.send(log_at(block)).await.unwrap();
+        }
+        tx.send(Ingest::BlocksComplete {
+            through: 3,
+            hash: hash_of(3),
+        })
+        .await
+        .unwrap();
+        drop(tx);
+
+        // Block 1's writes are discarded with the batch and routed again
+        let last = pipeline.run(rx, CancellationToken::new()).await.unwrap();
+        assert_eq!(last, Some(BlockNumber::new(3)));
+        assert_eq!(*router.routed.lock(), vec![1, 1, 3]);
+        assert_eq!(*store.discards.lock(), 1);
+        assert_eq!(*store.commits.lock(), vec![Some(3)]);
+    }
+
+    #[tokio::test]
+    async fn quarantined_log_is_skipped_without_batching() {
+        let router = PoisonRouter {
+            poisoned: vec![2],
+            ..PoisonRouter::default()
+        };
+        let store = CheckpointStore::default();
+        let pipeline = Pipeline::new(router.clone(), CheckpointManager::new(store.clone()));
+        let (tx, rx) = mpsc::channel(16);
+
+        for block in [1, 2, 3] {
+            tx.send(log_at(block)).await.unwrap();
+        }
+        tx.send(Ingest::BlocksComplete {
+            through: 3,
+            hash: hash_of(3),
+        })
+        .await
+        .unwrap();
+        drop(tx);
+
+        let last = pipeline.run(rx, CancellationToken::new()).await.unwrap();
+        assert_eq!(last, Some(BlockNumber::new(3)));
+        assert_eq!(*router.routed.lock(), vec![1, 3]);
+        assert_eq!(*store.discards.lock(), 0);
+    }
+
+    #[tokio::test]
+    async fn shutdown_ends_store_outage() {
+        let router = OutageRouter::failing_at(&[1]);
+        let store = CheckpointStore {
+            failed_pings: Arc::new(Mutex::new(u32::MAX)),
+            ..CheckpointStore::default()
+        };
+        let pipeline = Pipeline::new(router.clone(), CheckpointManager::new(store.clone()));
+        let (tx, rx) = mpsc::channel(16);
+        let shutdown = CancellationToken::new();
+
+        tx.send(log_at(1)).await.unwrap();
+        let cancel = {
+            let shutdown = shutdown.clone();
+            tokio::spawn(async move {
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                shutdown.cancel();
+            })
+        };
+
+        let result = pipeline.run(rx, shutdown).await;
+        cancel.await.unwrap();
+        assert!(result.is_err_and(|e| is_unavailable(&e)));
+        assert!(router.routed.lock().is_empty());
+        drop(tx);
+    }
+}
@@ -0,0 +1,1108 @@
+//! Quarantine of logs whose handler fails on them.
+//!
+//! Given a [`Quarantine`], the [`EventRouter`](super::EventRouter) isolates
+//! each log's handler errors instead of failing the log:
+//!
+//! - Transient store errors (a lost connection, an exhausted pool, a
+//!   timeout) are retried with doubling delays, a bounded number of times.
+//!   A store still unreachable after that fails the log as before, so the
+//!   [`Pipeline`](super::Pipeline) pauses until it answers again.
+//! - Any other error, e.g. a log that does not decode or a stake added to a
+//!   position that does not exist, quarantines the log in the
+//!   [`QuarantineStore`] with the handler and the error. Routing carries on
+//!   with the next log.
+//!
+//! # Strict Ordering
+//!
+//! A handler that folds the logs of an aggregate into one row, like the
+//! position handler, cannot handle a log whose predecessor it skipped. For
+//! handlers with strict ordering, every later log of an aggregate with a
+//! quarantined log is quarantined too, without being handled, and
+//! `blocked_by` the quarantined log before it. The aggregate is the log's
+//! first indexed topic: the user, for position events. Token transfers are
+//! applied whatever came before them, so the token handler is not strict by
+//! default.
+//!
+//! # Retrying
+//!
+//! Once the handler is fixed, the [`QuarantineRetrier`] routes quarantined
+//! logs again, in chain order (`ghostnet-indexer quarantine retry`), and
+//! releases those that go through. A blocked log is only retried once the log
+//! blocking it has been released.
+//!
+//! # Batched Writes
+//!
+//! In a batch, a failed statement takes the batch's transaction down with
+//! it, and a handler failing halfway may have written part of its log. A
+//! quarantined log that reached its handler therefore still fails, with
+//! [`AppError::Quarantined`], and the pipeline discards the batch and routes
+//! its logs again, skipping the quarantined ones. For the same reason a
+//! quarantine in front of a batched store should not retry; see
+//! [`Quarantine::with_retry`].
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::Arc;
+
+use alloy::primitives::B256;
+use alloy::rpc::types::Log;
+use parking_lot::Mutex;
+use tracing::{debug, error, info, instrument, warn};
+
+use crate::config::QuarantineSettings;
+use crate::error::{AppError, InfraError, Result};
+use crate::obs;
+use crate::ports::{IndexerStateStore, QuarantineStore};
+use crate::store::RetryPolicy;
+use crate::store::retry::{backoff, is_unavailable};
+use crate::types::entities::{QuarantinedEvent, RawLog};
+use crate::types::events::EventMetadata;
+
+use super::{EventKind, LogRouter};
+
+/// Block number, transaction index and log index of a log.
+type LogPosition = (u64, u64, u64);
+
+/// Position of the log received with `meta`.
+const fn position(meta: &EventMetadata) -> LogPosition {
+    (meta.block_number, meta.tx_index, meta.log_index)
+}
+
+/// Check if `error` may go away when the handler is called again.
+fn is_transient(error: &AppError) -> bool {
+    is_unavailable(error) || matches!(error, AppError::Infra(InfraError::Timeout(_)))
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// QUARANTINE
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Quarantined logs the quarantine knows of.
+///
+/// The store decides: an entry whose event is no longer in the store, e.g.
+/// because it was retried from the CLI, is dropped when it comes up.
+#[derive(Debug, Default)]
+struct State {
+    /// Quarantined logs, by block hash and log index.
+    logs: HashMap<(B256, u64), i64>,
+    /// Quarantined logs of strictly ordered aggregates, by handler and
+    /// aggregate, in chain order.
+    blocking: HashMap<(&'static str, B256), Vec<(LogPosition, i64)>>,
+}
+
+/// Isolates handler errors per log, quarantining the logs that keep failing.
+///
+/// See the [module docs](self).
+pub struct Quarantine {
+    store: Arc<dyn QuarantineStore>,
+    /// Handlers with strict ordering.
+    strict: Vec<&'static str>,
+    /// Retries of transient errors.
+    retry: RetryPolicy,
+    /// Quarantined logs at which a warning is logged.
+    warn_depth: u64,
+    /// Chain of the quarantine depth metric.
+    chain_id: u64,
+    state: Mutex<State>,
+}
+
+impl fmt::Debug for Quarantine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Quarantine")
+            .field("strict", &self.strict)
+            .field("retry", &self.retry)
+            .field("warn_depth", &self.warn_depth)
+            .field("chain_id", &self.chain_id)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Quarantine {
+    /// Create a quarantine writing to `store`.
+    ///
+    /// Handler names in `settings.strict_ordering` that no event is routed
+    /// to are ignored; [`Settings::validate`](crate::config::Settings::validate)
+    /// reports them.
+    #[must_use]
+    pub fn new(store: Arc<dyn QuarantineStore>, settings: &QuarantineSettings) -> Self {
+        let mut strict: Vec<&'static str> = EventKind::ALL
+            .into_iter()
+            .map(EventKind::handler)
+            .filter(|handler| settings.strict_ordering.iter().any(|name| name == handler))
+            .collect();
+        strict.dedup();
+
+        Self {
+            store,
+            strict,
+            retry: settings.retry_policy(),
+            warn_depth: settings.warn_depth,
+            chain_id: 0,
+            state: Mutex::new(State::default()),
+        }
+    }
+
+    /// Retry transient errors as `retry` says, instead of as the settings
+    /// did.
+    ///
+    /// Use [`RetryPolicy::NONE`] in front of a store that batches writes:
+    /// a statement that failed took the batch's transaction down, so calling
+    /// the handler again cannot succeed. The pipeline routes the batch again
+    /// once the store answers instead.
+    #[must_use]
+    pub const fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Set the chain the quarantine depth is reported for.
+    #[must_use]
+    pub const fn with_chain_id(mut self, chain_id: u64) -> Self {
+        self.chain_id = chain_id;
+        self
+    }
+
+    /// Pick up the logs quarantined before, e.g. by an earlier run, so they
+    /// are skipped and keep blocking their aggregates.
+    ///
+    /// Returns the number of quarantined logs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store cannot be read.
+    pub async fn load(&self) -> Result<u64> {
+        let events = self.store.list_quarantined().await?;
+        {
+            let mut state = self.state.lock();
+            *state = State::default();
+            for event in &events {
+                let log = &event.log;
+                state.logs.insert((log.block_hash, log.log_index), event.id);
+                if let Some(handler) = self.strict_handler(&event.handler)
+                    && let Some(aggregate) = event.aggregate
+                {
+                    let at = (log.block_number.value(), log.tx_index, log.log_index);
+                    state
+                        .blocking
+                        .entry((handler, aggregate))
+                        .or_default()
+                        .push((at, event.id));
+                }
+            }
+        }
+
+        let depth = events.len() as u64;
+        obs::set_quarantine_depth(self.chain_id, depth);
+        if depth >= self.warn_depth {
+            warn!(depth, "Quarantined logs waiting for a retry");
+        }
+        Ok(depth)
+    }
+
+    /// Call `dispatch` for the log of `kind`, quarantining the log if it
+    /// fails; see the [module docs](self).
+    ///
+    /// Returns `Ok(true)` if the log was handled, and `Ok(false)` if it was
+    /// quarantined without reaching its handler: because it was quarantined
+    /// before, because its aggregate is blocked, or because it did not
+    /// decode.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AppError::Quarantined`] if the log was quarantined after
+    /// its handler failed on it, the handler's error if the store is still
+    /// unavailable after the retries, and any error of quarantining.
+    pub(super) async fn isolate<F, Fut>(
+        &self,
+        kind: EventKind,
+        log: &Log,
+        meta: EventMetadata,
+        dispatch: F,
+    ) -> Result<bool>
+    where
+        F: Fn(EventMetadata) -> Fut + Send,
+        Fut: Future<Output = Result<()>> + Send,
+    {
+        let handler = kind.handler();
+        if self.is_quarantined(&meta).await? {
+            debug!(
+                handler,
+                block = meta.block_number,
+                "Skipping quarantined log"
+            );
+            return Ok(false);
+        }
+        if let Some(aggregate) = self.strict_aggregate(handler, log)
+            && let Some(blocker) = self.blocker(handler, aggregate, &meta).await?
+        {
+            let error = format!("blocked by quarantined event #{blocker}");
+            self.quarantine(kind, log, &meta, &error, Some(blocker))
+                .await?;
+            return Ok(false);
+        }
+
+        let mut delay = self.retry.delay;
+        let mut retries = 0;
+        let error = loop {
+            match dispatch(meta.clone()).await {
+                Ok(()) => return Ok(true),
+                Err(e) if retries < self.retry.max_retries && is_transient(&e) => {
+                    retries += 1;
+                    warn!(handler, attempt = retries, error = %e, "Handler failed, retrying");
+                    tokio::time::sleep(delay).await;
+                    delay = backoff(delay);
+                }
+                Err(e) => break e,
+            }
+        };
+        if is_unavailable(&error) {
+            return Err(error);
+        }
+
+        let id = self
+            .quarantine(kind, log, &meta, &error.to_string(), None)
+            .await?;
+        match error {
+            // Decoding failed before the handler could write anything
+            AppError::Infra(InfraError::EventDecoding(_)) => Ok(false),
+            _ => Err(AppError::Quarantined(id)),
+        }
+    }
+
+    /// Handler named `name`, if it has strict ordering.
+    fn strict_handler(&self, name: &str) -> Option<&'static str> {
+        self.strict.iter().copied().find(|handler| *handler == name)
+    }
+
+    /// Aggregate of `log`, if `handler` keeps strict ordering for it.
+    fn strict_aggregate(&self, handler: &'static str, log: &Log) -> Option<B256> {
+        self.strict_handler(handler)?;
+        log.topics().get(1).copied()
+    }
+
+    /// Check if the log received with `meta` is still quarantined.
+    async fn is_quarantined(&self, meta: &EventMetadata) -> Result<bool> {
+        let key = (meta.block_hash, meta.log_index);
+        let Some(id) = self.state.lock().logs.get(&key).copied() else {
+            return Ok(false);
+        };
+        if self.store.get_quarantined(id).await?.is_some() {
+            return Ok(true);
+        }
+        self.forget(id);
+        Ok(false)
+    }
+
+    /// Last quarantined log of `aggregate` before the log received with
+    /// `meta`, if any is still quarantined.
+    async fn blocker(
+        &self,
+        handler: &'static str,
+        aggregate: B256,
+        meta: &EventMetadata,
+    ) -> Result<Option<i64>> {
+        let at = position(meta);
+        loop {
+            let last = self
+                .state
+                .lock()
+                .blocking
+                .get(&(handler, aggregate))
+                .and_then(|logs| logs.iter().rev().find(|(logged, _)| *logged < at))
+                .map(|(_, id)| *id);
+            let Some(id) = last else {
+                return Ok(None);
+            };
+            if self.store.get_quarantined(id).await?.is_some() {
+                return Ok(Some(id));
+            }
+            self.forget(id);
+        }
+    }
+
+    /// Drop a log that is no longer quarantined.
+    fn forget(&self, id: i64) {
+        let mut state = self.state.lock();
+        state.logs.retain(|_, logged| *logged != id);
+        for logs in state.blocking.values_mut() {
+            logs.retain(|(_, logged)| *logged != id);
+        }
+        state.blocking.retain(|_, logs| !logs.is_empty());
+    }
+
+    /// Quarantine the log of `kind` received with `meta`.
+    async fn quarantine(
+        &self,
+        kind: EventKind,
+        log: &Log,
+        meta: &EventMetadata,
+        error: &str,
+        blocked_by: Option<i64>,
+    ) -> Result<i64> {
+        let handler = kind.handler();
+        let aggregate = log.topics().get(1).copied();
+        let id = self
+            .store
+            .quarantine_event(
+                &RawLog::new(log, meta),
+                handler,
+                aggregate,
+                error,
+                blocked_by,
+            )
+            .await?;
+        obs::record_quarantined(handler);
+
+        {
+            let mut state = self.state.lock();
+            let key = (meta.block_hash, meta.log_index);
+            if state.logs.insert(key, id).is_none()
+                && let Some(aggregate) = self.strict_aggregate(handler, log)
+            {
+                state
+                    .blocking
+                    .entry((handler, aggregate))
+                    .or_default()
+                    .push((position(meta), id));
+            }
+        }
+
+        let block = meta.block_number;
+        if let Some(blocked_by) = blocked_by {
+            warn!(
+                id,
+                handler, block, blocked_by, "Log quarantined behind its aggregate"
+            );
+        } else {
+            error!(
+                id,
+                handler,
+                event = ?kind,
+                block,
+                tx_hash = %meta.tx_hash,
+                error,
+                "Handler failed, log quarantined"
+            );
+        }
+
+        let depth = self.store.quarantine_depth().await?;
+        obs::set_quarantine_depth(self.chain_id, depth);
+        // Warn on reaching the threshold, and again at each multiple of it
+        if self.warn_depth > 0 && depth.is_multiple_of(self.warn_depth) {
+            warn!(depth, "Quarantined logs waiting for a retry");
+        }
+        Ok(id)
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// RETRIER
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Outcome of retrying a quarantined event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RetryOutcome {
+    /// Handled, and released from quarantine.
+    Released,
+    /// Not retried: the earlier quarantined event with this ID blocks it.
+    Blocked(i64),
+    /// Failed again with this error, and kept in quarantine.
+    Failed(String),
+}
+
+/// Outcome of retrying every quarantined event.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RetryReport {
+    /// Events handled and released.
+    pub released: u64,
+    /// Events not retried because an earlier one of their aggregate is
+    /// still quarantined.
+    pub blocked: u64,
+    /// Events that failed again.
+    pub failed: u64,
+}
+
+/// Routes quarantined events again, releasing those that go through.
+///
+/// # Type Parameters
+///
+/// * `S` - Store holding the quarantine, batching the handlers' writes
+/// * `R` - Router to the handlers, without a [`Quarantine`], which would
+///   skip the events it is given as quarantined
+#[derive(Debug)]
+pub struct QuarantineRetrier<S, R> {
+    store: Arc<S>,
+    router: R,
+}
+
+impl<S, R> QuarantineRetrier<S, R>
+where
+    S: QuarantineStore + IndexerStateStore,
+    R: LogRouter,
+{
+    /// Create a retrier routing through `router`.
+    ///
+    /// `store` should batch writes, so an event that fails again leaves
+    /// nothing behind.
+    pub const fn new(store: Arc<S>, router: R) -> Self {
+        Self { store, router }
+    }
+
+    /// Retry the quarantined event `id`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no event `id` is quarantined, or if the store
+    /// fails.
+    #[instrument(skip(self))]
+    pub async fn retry(&self, id: i64) -> Result<RetryOutcome> {
+        let events = self.store.list_quarantined().await?;
+        let index = events
+            .iter()
+            .position(|event| event.id == id)
+            .ok_or_else(|| AppError::Config(format!("No quarantined event #{id}")))?;
+        let earlier = events[..index].iter().map(|event| event.id).collect();
+        self.retry_event(&events[index], &earlier).await
+    }
+
+    /// Retry every quarantined event, in chain order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store fails; the events retried until then
+    /// stay released.
+    #[instrument(skip(self))]
+    pub async fn retry_all(&self) -> Result<RetryReport> {
+        let events = self.store.list_quarantined().await?;
+        let mut kept = HashSet::new();
+        let mut report = RetryReport::default();
+        for event in &events {
+            match self.retry_event(event, &kept).await? {
+                RetryOutcome::Released => {
+                    report.released += 1;
+                    continue;
+                }
+                RetryOutcome::Blocked(_) => report.blocked += 1,
+                RetryOutcome::Failed(_) => report.failed += 1,
+            }
+            // Events still quarantined keep blocking later ones
+            kept.insert(event.id);
+        }
+
+        info!(
+            released = report.released,
+            blocked = report.blocked,
+            failed = report.failed,
+            "Quarantined events retried"
+        );
+        Ok(report)
+    }
+
+    /// Route `event` again, unless one of the `quarantined` events blocks it.
+    async fn retry_event(
+        &self,
+        event: &QuarantinedEvent,
+        quarantined: &HashSet<i64>,
+    ) -> Result<RetryOutcome> {
+        if let Some(blocker) = event.blocked_by.filter(|id| quarantined.contains(id)) {
+            debug!(id = event.id, blocker, "Quarantined event still blocked");
+            return Ok(RetryOutcome::Blocked(blocker));
+        }
+
+        match self
+            .router
+            .route_log(&event.log.log(), event.log.metadata())
+            .await
+        {
+            Ok(_) => {
+                self.store.commit_batch().await?;
+                self.store.release_quarantined(event.id).await?;
+                info!(id = event.id, handler = %event.handler, "Quarantined event released");
+                Ok(RetryOutcome::Released)
+            }
+            Err(e) => {
+                self.store.discard_batch().await?;
+                if is_unavailable(&e) {
+                    return Err(e);
+                }
+                let error = e.to_string();
+                self.store
+                    .record_quarantine_attempt(event.id, &error)
+                    .await?;
+                warn!(id = event.id, handler = %event.handler, error, "Quarantined event failed again");
+                Ok(RetryOutcome::Failed(error))
+            }
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::significant_drop_tightening)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::time::Duration;
+
+    use alloy::primitives::{Address, Bytes, LogData, U256};
+    use alloy::sol_types::SolEvent;
+    use async_trait::async_trait;
+    use chrono::Utc;
+
+    use super::*;
+    use crate::abi::{data_token, ghost_core};
+    use crate::error::DomainError;
+    use crate::handlers::PositionPort;
+    use crate::handlers::mocks::CountingHandler;
+    use crate::indexer::{Contract, EventRouter};
+    use crate::types::primitives::BlockNumber;
+
+    /// Position handler failing on the positions of `broken` users, with a
+    /// store error for the first `outages` calls.
+    #[derive(Debug, Default, Clone)]
+    struct FlakyPositions {
+        broken: Arc<Mutex<HashSet<Address>>>,
+        outages: Arc<AtomicU32>,
+        handled: Arc<Mutex<Vec<(Address, u64)>>>,
+    }
+
+    impl FlakyPositions {
+        fn breaking(user: Address) -> Self {
+            let positions = Self::default();
+            positions.broken.lock().insert(user);
+            positions
+        }
+
+        /// "Deploy the fix": stop failing on any user.
+        fn fix(&self) {
+            self.broken.lock().clear();
+        }
+
+        fn handle(&self, user: Address, meta: &EventMetadata) -> Result<()> {
+            if self
+                .outages
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                .is_ok()
+            {
+                return Err(InfraError::Database(sqlx::Error::PoolTimedOut).into());
+            }
+            if self.broken.lock().contains(&user) {
+                return Err(DomainError::PositionNotFound(user.to_string()).into());
+            }
+            self.handled.lock().push((user, meta.block_number));
+            Ok(())
+        }
+
+        fn handled(&self) -> Vec<(Address, u64)> {
+            self.handled.lock().clone()
+        }
+    }
+
+    #[async_trait]
+    impl PositionPort for FlakyPositions {
+        async fn handle_jacked_in(
+            &self,
+            e: ghost_core::JackedIn,
+            meta: EventMetadata,
+        ) -> Result<()> {
+            self.handle(e.user, &meta)
+        }
+
+        async fn handle_stake_added(
+            &self,
+            e: ghost_core::StakeAdded,
+            meta: EventMetadata,
+        ) -> Result<()> {
+            self.handle(e.user, &meta)
+        }
+
+        async fn handle_extracted(
+            &self,
+            e: ghost_core::Extracted,
+            meta: EventMetadata,
+        ) -> Result<()> {
+            self.handle(e.user, &meta)
+        }
+
+        async fn handle_boost_applied(
+            &self,
+            e: ghost_core::BoostApplied,
+            meta: EventMetadata,
+        ) -> Result<()> {
+            self.handle(e.user, &meta)
+        }
+
+        async fn handle_position_culled(
+            &self,
+            e: ghost_core::PositionCulled,
+            meta: EventMetadata,
+        ) -> Result<()> {
+            self.handle(e.victim, &meta)
+        }
+    }
+
+    /// Quarantine and batch in memory; every commit is counted.
+    #[derive(Debug, Default)]
+    struct MemoryQuarantine {
+        events: Mutex<Vec<QuarantinedEvent>>,
+        commits: AtomicU32,
+    }
+
+    impl MemoryQuarantine {
+        fn events(&self) -> Vec<QuarantinedEvent> {
+            self.events.lock().clone()
+        }
+    }
+
+    #[async_trait]
+    impl QuarantineStore for MemoryQuarantine {
+        async fn quarantine_event(
+            &self,
+            log: &RawLog,
+            handler: &str,
+            aggregate: Option<B256>,
+            error: &str,
+            blocked_by: Option<i64>,
+        ) -> Result<i64> {
+            let mut events = self.events.lock();
+            let id = events.iter().map(|event| event.id).max().unwrap_or(0) + 1;
+            events.push(QuarantinedEvent {
+                id,
+                log: log.clone(),
+                handler: handler.into(),
+                aggregate,
+                error: error.into(),
+                blocked_by,
+                attempts: 0,
+                quarantined_at: Utc::now(),
+                last_attempt_at: None,
+            });
+            events.sort_by_key(|event| (event.log.block_number, event.log.log_index));
+            Ok(id)
+        }
+
+        async fn list_quarantined(&self) -> Result<Vec<QuarantinedEvent>> {
+            Ok(self.events())
+        }
+
+        async fn get_quarantined(&self, id: i64) -> Result<Option<QuarantinedEvent>> {
+            Ok(self.events().into_iter().find(|event| event.id == id))
+        }
+
+        async fn record_quarantine_attempt(&self, id: i64, error: &str) -> Result<()> {
+            let mut events = self.events.lock();
+            let event = events.iter_mut().find(|event| event.id == id).unwrap();
+            event.attempts += 1;
+            event.error = error.into();
+            event.last_attempt_at = Some(Utc::now());
+            Ok(())
+        }
+
+        async fn release_quarantined(&self, id: i64) -> Result<bool> {
+            let mut events = self.events.lock();
+            let before = events.len();
+            events.retain(|event| event.id != id);
+            Ok(events.len() < before)
+        }
+
+        async fn quarantine_depth(&self) -> Result<u64> {
+            Ok(self.events.lock().len() as u64)
+        }
+    }
+
+    #[async_trait]
+    impl IndexerStateStore for MemoryQuarantine {
+        async fn get_last_block(&self) -> Result<BlockNumber> {
+            Ok(BlockNumber::new(0))
+        }
+
+        async fn set_last_block(&self, _block: BlockNumber, _hash: B256) -> Result<()> {
+            Ok(())
+        }
+
+        async fn insert_block_hash(
+            &self,
+            _block: BlockNumber,
+            _hash: B256,
+            _parent: B256,
+            _timestamp: u64,
+        ) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_block_hash(&self, _block: BlockNumber) -> Result<Option<B256>> {
+            Ok(None)
+        }
+
+        async fn execute_reorg_rollback(&self, _fork_point: BlockNumber) -> Result<()> {
+            Ok(())
+        }
+
+        async fn prune_old_blocks(&self, _keep_blocks: u64) -> Result<u64> {
+            Ok(0)
+        }
+
+        async fn get_cursor(&self, _contract: Contract) -> Result<Option<BlockNumber>> {
+            Ok(None)
+        }
+
+        async fn set_cursor(&self, _contract: Contract, _block: BlockNumber) -> Result<()> {
+            Ok(())
+        }
+
+        async fn min_cursor(&self, _contracts: &[Contract]) -> Result<Option<BlockNumber>> {
+            Ok(None)
+        }
+
+        async fn commit_batch(&self) -> Result<()> {
+            self.commits.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    type Router = EventRouter<
+        FlakyPositions,
+        CountingHandler,
+        CountingHandler,
+        CountingHandler,
+        CountingHandler,
+        CountingHandler,
+        CountingHandler,
+    >;
+
+    fn settings() -> QuarantineSettings {
+        QuarantineSettings {
+            retry_delay_ms: 1,
+            ..QuarantineSettings::default()
+        }
+    }
+
+    /// Router to `positions` and a counting token handler, quarantining in
+    /// `store` per `settings` if given.
+    fn router_for(
+        positions: &FlakyPositions,
+        tokens: &CountingHandler,
+        store: &Arc<MemoryQuarantine>,
+        settings: Option<&QuarantineSettings>,
+    ) -> Router {
+        let quarantine = settings.map(|settings| Quarantine::new(store.clone(), settings));
+        EventRouter::new(
+            positions.clone(),
+            CountingHandler::new(),
+            CountingHandler::new(),
+            CountingHandler::new(),
+            tokens.clone(),
+            CountingHandler::new(),
+            CountingHandler::new(),
+        )
+        .with_quarantine(quarantine)
+    }
+
+    /// Router to `positions` and `tokens` through `quarantine`.
+    fn router_with(
+        positions: &FlakyPositions,
+        tokens: &CountingHandler,
+        quarantine: Quarantine,
+    ) -> Router {
+        EventRouter::new(
+            positions.clone(),
+            CountingHandler::new(),
+            CountingHandler::new(),
+            CountingHandler::new(),
+            tokens.clone(),
+            CountingHandler::new(),
+            CountingHandler::new(),
+        )
+        .with_quarantine(Some(quarantine))
+    }
+
+    fn log_at(data: LogData, block: u64) -> (Log, EventMetadata) {
+        let meta = EventMetadata {
+            block_number: block,
+            block_hash: B256::left_padding_from(&block.to_be_bytes()),
+            tx_hash: B256::ZERO,
+            tx_index: 0,
+            log_index: 0,
+            timestamp: Utc::now(),
+            contract: Address::ZERO,
+            chain_id: 6343,
+            tx_function: None,
+            tx_from: None,
+        };
+        let log = Log {
+            inner: alloy::primitives::Log {
+                address: Address::ZERO,
+                data,
+            },
+            block_hash: Some(meta.block_hash),
+            block_number: Some(block),
+            ..Log::default()
+        };
+        (log, meta)
+    }
+
+    fn stake(user: Address, block: u64) -> (Log, EventMetadata) {
+        let event = ghost_core::StakeAdded {
+            user,
+            amount: U256::from(100),
+            newTotal: U256::from(200),
+        };
+        log_at(event.encode_log_data(), block)
+    }
+
+    fn transfer(from: Address, block: u64) -> (Log, EventMetadata) {
+        let event = data_token::Transfer {
+            from,
+            to: Address::repeat_byte(0xEE),
+            value: U256::from(100),
+        };
+        log_at(event.encode_log_data(), block)
+    }
+
+    async fn route(router: &Router, (log, meta): (Log, EventMetadata)) -> Result<bool> {
+        router.route_log(&log, meta).await
+    }
+
+    const ALICE: Address = Address::repeat_byte(0xA1);
+    const BOB: Address = Address::repeat_byte(0xB0);
+
+    #[tokio::test]
+    async fn failing_log_is_quarantined_and_later_logs_still_route() {
+        let store = Arc::new(MemoryQuarantine::default());
+        let positions = FlakyPositions::breaking(ALICE);
+        let tokens = CountingHandler::new();
+        let router = router_for(&positions, &tokens, &store, Some(&settings()));
+
+        assert!(route(&router, stake(BOB, 1)).await.unwrap());
+        let failed = route(&router, stake(ALICE, 2)).await.unwrap_err();
+        assert!(route(&router, transfer(BOB, 3)).await.unwrap());
+        assert!(route(&router, stake(BOB, 4)).await.unwrap());
+
+        let events = store.events();
+        assert_eq!(events.len(), 1);
+        assert!(matches!(failed, AppError::Quarantined(id) if id == events[0].id));
+        assert_eq!(events[0].handler, "position");
+        assert_eq!(events[0].log.block_number, BlockNumber::new(2));
+        assert_eq!(events[0].aggregate, Some(ALICE.into_word()));
+        assert!(
+            events[0].error.contains("position not found"),
+            "{}",
+            events[0].error
+        );
+        assert_eq!(positions.handled(), vec![(BOB, 1), (BOB, 4)]);
+        assert_eq!(tokens.count(), 1);
+    }
+
+    #[tokio::test]
+    async fn strict_ordering_quarantines_later_logs_of_the_aggregate() {
+        let store = Arc::new(MemoryQuarantine::default());
+        let positions = FlakyPositions::breaking(ALICE);
+        let tokens = CountingHandler::new();
+        let router = router_for(&positions, &tokens, &store, Some(&settings()));
+
+        assert!(route(&router, stake(ALICE, 1)).await.is_err());
+        // Fixed or not, Alice's later position logs wait for the first
+        positions.fix();
+        assert!(!route(&router, stake(ALICE, 2)).await.unwrap());
+        assert!(!route(&router, stake(ALICE, 3)).await.unwrap());
+        // Transfers are not strictly ordered, and Bob is another aggregate
+        assert!(route(&router, transfer(ALICE, 4)).await.unwrap());
+        assert!(route(&router, stake(BOB, 5)).await.unwrap());
+
+        let events = store.events();
+        let blocked_by: Vec<_> = events.iter().map(|event| event.blocked_by).collect();
+        assert_eq!(
+            blocked_by,
+            vec![None, Some(events[0].id), Some(events[1].id)]
+        );
+        assert_eq!(
+            events[2].error,
+            format!("blocked by quarantined event #{}", events[1].id)
+        );
+        assert_eq!(positions.handled(), vec![(BOB, 5)]);
+        assert_eq!(tokens.count(), 1);
+    }
+
+    #[tokio::test]
+    async fn handlers_without_strict_ordering_do_not_block() {
+        let store = Arc::new(MemoryQuarantine::default());
+        let positions = FlakyPositions::breaking(ALICE);
+        let tokens = CountingHandler::new();
+        let settings = QuarantineSettings {
+            strict_ordering: vec![],
+            ..settings()
+        };
+        let router = router_for(&positions, &tokens, &store, Some(&settings));
+
+        assert!(route(&router, stake(ALICE, 1)).await.is_err());
+        positions.fix();
+        assert!(route(&router, stake(ALICE, 2)).await.unwrap());
+
+        assert_eq!(store.events().len(), 1);
+        assert_eq!(positions.handled(), vec![(ALICE, 2)]);
+    }
+
+    #[tokio::test]
+    async fn quarantined_log_is_skipped_when_routed_again() {
+        let store = Arc::new(MemoryQuarantine::default());
+        let positions = FlakyPositions::breaking(ALICE);
+        let tokens = CountingHandler::new();
+        let router = router_for(&positions, &tokens, &store, Some(&settings()));
+
+        assert!(route(&router, stake(ALICE, 1)).await.is_err());
+        // E.g. the pipeline routing a discarded batch again
+        positions.fix();
+        assert!(!route(&router, stake(ALICE, 1)).await.unwrap());
+        assert_eq!(store.events().len(), 1);
+        assert!(positions.handled().is_empty());
+
+        // A new run picks up the quarantine and its blocked aggregates
+        let quarantine = Quarantine::new(store.clone(), &settings());
+        assert_eq!(quarantine.load().await.unwrap(), 1);
+        let router = router_with(&positions, &tokens, quarantine);
+        assert!(!route(&router, stake(ALICE, 1)).await.unwrap());
+        assert!(!route(&router, stake(ALICE, 2)).await.unwrap());
+        assert_eq!(store.events().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn undecodable_log_is_quarantined_without_failing() {
+        let store = Arc::new(MemoryQuarantine::default());
+        let positions = FlakyPositions::default();
+        let tokens = CountingHandler::new();
+        let router = router_for(&positions, &tokens, &store, Some(&settings()));
+
+        // StakeAdded's topics with its data cut short
+        let data = LogData::new_unchecked(
+            vec![ghost_core::StakeAdded::SIGNATURE_HASH, ALICE.into_word()],
+            Bytes::from_static(&[1, 2, 3]),
+        );
+        assert!(!route(&router, log_at(data, 1)).await.unwrap());
+
+        let events = store.events();
+        assert_eq!(events.len(), 1);
+        assert!(events[0].error.contains("decoding"), "{}", events[0].error);
+        // Strict ordering applies to logs that did not decode too
+        assert!(!route(&router, stake(ALICE, 2)).await.unwrap());
+        assert!(positions.handled().is_empty());
+    }
+
+    #[tokio::test]
+    async fn transient_errors_are_retried_before_quarantining() {
+        let store = Arc::new(MemoryQuarantine::default());
+        let positions = FlakyPositions::default();
+        let tokens = CountingHandler::new();
+        let router = router_for(&positions, &tokens, &store, Some(&settings()));
+
+        // Two outages are within the three retries
+        positions.outages.store(2, Ordering::SeqCst);
+        assert!(route(&router, stake(ALICE, 1)).await.unwrap());
+        assert_eq!(positions.handled(), vec![(ALICE, 1)]);
+
+        // A store still down fails the log, for the pipeline to pause on
+        positions.outages.store(4, Ordering::SeqCst);
+        let error = route(&router, stake(ALICE, 2)).await.unwrap_err();
+        assert!(is_unavailable(&error), "{error}");
+        assert!(store.events().is_empty());
+    }
+
+    #[tokio::test]
+    async fn batched_quarantine_does_not_retry() {
+        let store = Arc::new(MemoryQuarantine::default());
+        let positions = FlakyPositions::default();
+        let tokens = CountingHandler::new();
+        let quarantine = Quarantine::new(store.clone(), &settings()).with_retry(RetryPolicy::NONE);
+        let router = router_with(&positions, &tokens, quarantine);
+
+        positions.outages.store(1, Ordering::SeqCst);
+        let error = route(&router, stake(ALICE, 1)).await.unwrap_err();
+        assert!(is_unavailable(&error), "{error}");
+        assert!(store.events().is_empty());
+    }
+
+    #[tokio::test]
+    async fn retry_succeeds_once_the_handler_is_fixed() {
+        let store = Arc::new(MemoryQuarantine::default());
+        let positions = FlakyPositions::breaking(ALICE);
+        let tokens = CountingHandler::new();
+        let router = router_for(&positions, &tokens, &store, Some(&settings()));
+        assert!(route(&router, stake(ALICE, 1)).await.is_err());
+        let id = store.events()[0].id;
+
+        let retrier =
+            QuarantineRetrier::new(store.clone(), router_for(&positions, &tokens, &store, None));
+        let outcome = retrier.retry(id).await.unwrap();
+        assert!(matches!(outcome, RetryOutcome::Failed(ref e) if e.contains("position not found")));
+        assert_eq!(store.events()[0].attempts, 1);
+        assert_eq!(store.commits.load(Ordering::SeqCst), 0);
+
+        positions.fix();
+        assert_eq!(retrier.retry(id).await.unwrap(), RetryOutcome::Released);
+        assert!(store.events().is_empty());
+        assert_eq!(store.commits.load(Ordering::SeqCst), 1);
+        assert_eq!(positions.handled(), vec![(ALICE, 1)]);
+
+        assert!(retrier.retry(id).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn retry_keeps_the_order_of_an_aggregate() {
+        let store = Arc::new(MemoryQuarantine::default());
+        let positions = FlakyPositions::breaking(ALICE);
+        let tokens = CountingHandler::new();
+        let router = router_for(&positions, &tokens, &store, Some(&settings()));
+        assert!(route(&router, stake(ALICE, 1)).await.is_err());
+        assert!(!route(&router, stake(ALICE, 2)).await.unwrap());
+        assert!(!route(&router, stake(ALICE, 3)).await.unwrap());
+        let ids: Vec<_> = store.events().iter().map(|event| event.id).collect();
+
+        let retrier =
+            QuarantineRetrier::new(store.clone(), router_for(&positions, &tokens, &store, None));
+        // The second waits for the first, fixed or not
+        assert_eq!(
+            retrier.retry(ids[1]).await.unwrap(),
+            RetryOutcome::Blocked(ids[0])
+        );
+        let report = retrier.retry_all().await.unwrap();
+        assert_eq!(
+            report,
+            RetryReport {
+                released: 0,
+                blocked: 2,
+                failed: 1,
+            }
+        );
+
+        positions.fix();
+        let report = retrier.retry_all().await.unwrap();
+        assert_eq!(report.released, 3);
+        assert!(store.events().is_empty());
+        assert_eq!(
+            positions.handled(),
+            vec![(ALICE, 1), (ALICE, 2), (ALICE, 3)]
+        );
+
+        // Released from the CLI, Alice no longer blocks the running router
+        assert!(route(&router, stake(ALICE, 4)).await.unwrap());
+        assert!(store.events().is_empty());
+    }
+
+    #[test]
+    fn strict_handlers_come_from_settings() {
+        let store: Arc<dyn QuarantineStore> = Arc::new(MemoryQuarantine::default());
+        let settings = QuarantineSettings {
+            strict_ordering: vec!["token".into(), "position".into(), "nonsense".into()],
+            max_retries: 5,
+            retry_delay_ms: 20,
+            ..QuarantineSettings::default()
+        };
+        let quarantine = Quarantine::new(store, &settings);
+        assert_eq!(quarantine.strict, vec!["position", "token"]);
+        assert_eq!(
+            quarantine.retry,
+            RetryPolicy::new(5, Duration::from_millis(20))
+        );
+    }
+}
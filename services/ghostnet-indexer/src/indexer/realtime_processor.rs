@@ -2,7 +2,8 @@
 //!
 //! `MegaETH` executes transactions within 10ms and exposes results via a
 //! real-time WebSocket API. This processor subscribes to contract logs
-//! and receives events as soon as they're packaged into mini-blocks.
+//! through [`MegaEthWsClient`] and receives events as soon as they're
+//! packaged into mini-blocks.
 //!
 //! # `MegaETH` Realtime API
 //!
@@ -28,17 +29,22 @@
 //! │                     RealtimeProcessor                              │
 //! │                                                                   │
 //! │  ┌──────────────┐    ┌─────────────────┐    ┌──────────────────┐ │
-//! │  │  WebSocket   │───▶│  Log Stream     │───▶│  Dispatch to     │ │
-//! │  │  Connection  │    │  Subscription   │    │  EventRouter     │ │
+//! │  │ MegaEthWs-   │───▶│  Log Stream     │───▶│  Dispatch to     │ │
+//! │  │ Client       │    │  Subscription   │    │  EventRouter     │ │
+//! │  └──────────────┘    └─────────────────┘    └────────▲─────────┘ │
+//! │                                                      │ timestamp │
+//! │  ┌──────────────┐    ┌─────────────────┐    ┌────────┴─────────┐ │
+//! │  │  Keep-alive  │───▶│  Lookup         │───▶│  Block Cache     │ │
+//! │  │  Task        │    │  Connection     │    │                  │ │
 //! │  └──────────────┘    └─────────────────┘    └──────────────────┘ │
-//! │         │                                                        │
-//! │         │            ┌─────────────────┐                         │
-//! │         └───────────▶│  Keep-alive     │ (eth_chainId @ 30s)    │
-//! │                      │  Task           │                         │
-//! │                      └─────────────────┘                         │
 //! └───────────────────────────────────────────────────────────────────┘
 //! ```
 //!
+//! The log subscription keeps itself alive; its reconnects are handled here
+//! ([`ReconnectPolicy::Disabled`]) so the reconnect budget can be reset after
+//! a stable session. Block timestamps are looked up over a second connection
+//! kept alive by its own task.
+//!
 //! # Usage
 //!
 //! ```ignore
@@ -51,23 +57,27 @@
 //! processor.start(shutdown).await?;
 //! ```
 
+use std::sync::Arc;
 use std::time::Duration;
 
-use alloy::primitives::Address;
 use alloy::providers::{Provider, ProviderBuilder, WsConnect};
-use alloy::rpc::types::{Filter, Log};
+use alloy::rpc::types::Log;
 use chrono::{DateTime, Utc};
 use futures::StreamExt;
 use moka::future::Cache as MokaCache;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, oneshot};
 use tokio::time::{interval, timeout};
 use tokio_util::sync::CancellationToken;
 use tracing::{debug, error, info, instrument, warn};
 
-use crate::config::ContractAddresses;
+use megaeth_rpc::{MegaEthWsClient, ReconnectPolicy, WsConfig};
+
 use crate::error::{InfraError, Result};
+use crate::obs::{self, LogSource};
 use crate::types::events::EventMetadata;
 
+use super::{Ingest, SharedRegistry, TxContextResolver};
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // CONSTANTS
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -140,6 +150,8 @@ enum SubscriptionResult {
     /// Connection failed AFTER successfully processing logs.
     /// The reconnect counter SHOULD be reset since we had a stable connection.
     FailedAfterActivity(crate::error::AppError),
+    /// The contract registry was replaced; subscribe again with its filter.
+    RegistryChanged,
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -153,25 +165,34 @@ enum SubscriptionResult {
 pub struct RealtimeProcessor {
     /// WebSocket URL for `MegaETH` RPC.
     ws_url: String,
-    /// Parsed contract addresses to monitor.
-    contract_addresses: Vec<Address>,
+    /// Client for the log subscription.
+    ws_client: MegaEthWsClient,
+    /// Chain indexed, stamped on the metadata of every event.
+    chain_id: u64,
+    /// Contracts to monitor; a replacement restarts the subscription.
+    contracts: SharedRegistry,
     /// Channel for sending logs to the event router.
-    log_sender: mpsc::Sender<(Log, EventMetadata)>,
+    log_sender: mpsc::Sender<Ingest>,
     /// Cache for block timestamps to avoid redundant RPC calls.
     /// Key: block number, Value: block timestamp.
     block_cache: MokaCache<u64, DateTime<Utc>>,
+    /// Resolves the transaction context of events, when enabled.
+    tx_context: Option<Arc<TxContextResolver>>,
 }
 
 impl std::fmt::Debug for RealtimeProcessor {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("RealtimeProcessor")
             .field("ws_url", &self.ws_url)
-            .field("contract_addresses", &self.contract_addresses)
+            .field("ws_client", &self.ws_client)
+            .field("chain_id", &self.chain_id)
+            .field("contracts", &self.contracts)
             .field("log_sender", &"<Sender>")
             .field(
                 "block_cache",
                 &format!("<Cache entries={}>", self.block_cache.entry_count()),
             )
+            .field("tx_context", &self.tx_context)
             .finish()
     }
 }
@@ -182,20 +203,25 @@ impl RealtimeProcessor {
     /// # Arguments
     ///
     /// * `ws_url` - WebSocket URL for `MegaETH` RPC (e.g., `wss://rpc.megaeth.io/ws`)
-    /// * `contracts` - Contract addresses to monitor
+    /// * `contracts` - Contracts to monitor, or a [`SharedRegistry`] to pick
+    ///   up replacements from
     /// * `log_sender` - Channel for dispatching logs to the event router
     ///
     /// # Errors
     ///
-    /// Returns an error if contract addresses cannot be parsed.
+    /// Returns an error if `ws_url` is not a WebSocket URL.
     pub fn new(
         ws_url: impl Into<String>,
-        contracts: &ContractAddresses,
-        log_sender: mpsc::Sender<(Log, EventMetadata)>,
+        contracts: impl Into<SharedRegistry>,
+        log_sender: mpsc::Sender<Ingest>,
     ) -> Result<Self> {
-        let contract_addresses = contracts
-            .parse_all()
-            .map_err(|e| InfraError::AddressParsing(format!("Invalid contract address: {e}")))?;
+        let ws_url = ws_url.into();
+        let ws_config = WsConfig::default()
+            .with_keepalive_interval(KEEPALIVE_INTERVAL)
+            .with_connect_timeout(CONNECTION_TIMEOUT)
+            .with_reconnect(ReconnectPolicy::Disabled);
+        let ws_client =
+            MegaEthWsClient::with_config(&ws_url, ws_config).map_err(InfraError::MegaEth)?;
 
         // Build block timestamp cache with TTL-based eviction.
         // Block timestamps are immutable once confirmed, so we can cache aggressively.
@@ -205,13 +231,33 @@ impl RealtimeProcessor {
             .build();
 
         Ok(Self {
-            ws_url: ws_url.into(),
-            contract_addresses,
+            ws_url,
+            ws_client,
+            chain_id: 0,
+            contracts: contracts.into(),
             log_sender,
             block_cache,
+            tx_context: None,
         })
     }
 
+    /// Stamp events with `chain_id`, the chain the WebSocket serves.
+    #[must_use]
+    pub const fn with_chain_id(mut self, chain_id: u64) -> Self {
+        self.chain_id = chain_id;
+        self
+    }
+
+    /// Attach transaction context to the metadata of every event.
+    ///
+    /// Transactions are fetched through the resolver's provider, not the
+    /// WebSocket connection.
+    #[must_use]
+    pub fn with_tx_context(mut self, resolver: Arc<TxContextResolver>) -> Self {
+        self.tx_context = Some(resolver);
+        self
+    }
+
     /// Start the realtime processor.
     ///
     /// This method connects to the WebSocket, subscribes to logs, and processes
@@ -246,6 +292,9 @@ impl RealtimeProcessor {
                     info!("Realtime processor stopped cleanly");
                     return Ok(());
                 }
+                SubscriptionResult::RegistryChanged => {
+                    info!("Contract registry replaced, resubscribing");
+                }
                 SubscriptionResult::FailedAfterActivity(e) => {
                     // Had successful activity before failure - reset counter
                     // This handles the case where we ran successfully for hours
@@ -320,7 +369,7 @@ impl RealtimeProcessor {
     /// Returns `SubscriptionResult` indicating how the session ended and whether
     /// successful activity occurred (which affects reconnect counter behavior).
     async fn run_subscription(&self, shutdown: &CancellationToken) -> SubscriptionResult {
-        // Connect with timeout, but respect shutdown
+        // Connect the block timestamp lookup connection with timeout, but respect shutdown
         let ws = WsConnect::new(&self.ws_url);
         let provider = tokio::select! {
             () = shutdown.cancelled() => {
@@ -343,62 +392,37 @@ impl RealtimeProcessor {
             }
         };
 
-        info!("WebSocket connected");
+        info!("Lookup connection established");
 
         // Build filter for all contracts with pending block tags (MegaETH Realtime API)
         // Note: The "pending" tag gives us mini-block level granularity (~10ms)
-        let filter = Filter::new()
-            .address(self.contract_addresses.clone())
+        let mut registry_updates = self.contracts.subscribe();
+        let contracts = Arc::clone(&registry_updates.borrow_and_update());
+        let filter = contracts
+            .log_filter()
             .from_block(alloy::eips::BlockNumberOrTag::Pending)
             .to_block(alloy::eips::BlockNumberOrTag::Pending);
 
         // Subscribe to logs
-        let subscription = match provider.subscribe_logs(&filter).await {
+        let mut log_stream = match self.ws_client.subscribe_logs(&filter).await {
             Ok(s) => s,
             Err(e) => {
-                return SubscriptionResult::FailedBeforeActivity(
-                    InfraError::Rpc(Box::new(e)).into(),
-                );
+                return SubscriptionResult::FailedBeforeActivity(InfraError::MegaEth(e).into());
             }
         };
 
         info!(
-            contracts = self.contract_addresses.len(),
+            contracts = contracts.contracts().count(),
+            subscription = log_stream.id(),
             "Subscribed to realtime logs"
         );
 
-        // Convert to stream
-        let mut log_stream = subscription.into_stream();
-
         // Track whether we've successfully processed any logs.
         // If we have, a subsequent disconnect should reset the reconnect counter.
         let mut processed_logs = false;
 
-        // Keep-alive task: send eth_chainId every 25 seconds
-        // Uses a oneshot channel to signal failure back to the main loop
-        let (keepalive_failed_tx, mut keepalive_failed_rx) = tokio::sync::oneshot::channel::<()>();
-        let provider_clone = provider.clone();
-        let shutdown_clone = shutdown.clone();
-        tokio::spawn(async move {
-            let mut keepalive_timer = interval(KEEPALIVE_INTERVAL);
-            loop {
-                tokio::select! {
-                    () = shutdown_clone.cancelled() => {
-                        debug!("Keep-alive task stopping due to shutdown");
-                        return;
-                    }
-                    _ = keepalive_timer.tick() => {
-                        if let Err(e) = provider_clone.get_chain_id().await {
-                            warn!(error = ?e, "Keep-alive ping failed");
-                            // Signal failure to main loop (ignore send error if receiver dropped)
-                            let _ = keepalive_failed_tx.send(());
-                            return;
-                        }
-                        debug!("Keep-alive ping sent");
-                    }
-                }
-            }
-        });
+        // The log subscription keeps itself alive; the lookup connection needs its own pings
+        let mut keepalive_failed_rx = Self::spawn_keepalive(provider.clone(), shutdown.clone());
 
         // Process logs
         loop {
@@ -409,6 +433,11 @@ impl RealtimeProcessor {
                     return SubscriptionResult::CleanShutdown;
                 }
 
+                // Subscribe again when the filter changes
+                Ok(()) = registry_updates.changed() => {
+                    return SubscriptionResult::RegistryChanged;
+                }
+
                 // Check if keep-alive task failed
                 Ok(()) = &mut keepalive_failed_rx => {
                     warn!("Keep-alive task failed, reconnecting");
@@ -422,8 +451,23 @@ impl RealtimeProcessor {
 
                 // Process incoming logs
                 maybe_log = log_stream.next() => {
-                    if let Some(log) = maybe_log {
-                        if let Err(e) = self.dispatch_log(&provider, log).await {
+                    if let Some(Err(e)) = maybe_log {
+                        if e.is_retryable() {
+                            // Connection lost
+                            warn!(error = %e, "Log subscription failed");
+                            let err = InfraError::MegaEth(e).into();
+                            return if processed_logs {
+                                SubscriptionResult::FailedAfterActivity(err)
+                            } else {
+                                SubscriptionResult::FailedBeforeActivity(err)
+                            };
+                        }
+                        error!(error = %e, "Failed to decode realtime log");
+                    } else if let Some(Ok(log)) = maybe_log {
+                        obs::record_logs_received(LogSource::Ws, 1);
+                        if !contracts.observe(&log) {
+                            // Counted by the registry
+                        } else if let Err(e) = self.dispatch_log(&provider, log).await {
                             error!(error = ?e, "Failed to dispatch log");
                             // Continue processing - don't disconnect for single log failures
                         } else {
@@ -445,6 +489,38 @@ impl RealtimeProcessor {
         }
     }
 
+    /// Spawn the keep-alive task for the lookup connection.
+    ///
+    /// Sends `eth_chainId` every 25 seconds. The returned receiver fires if a
+    /// ping fails.
+    fn spawn_keepalive<P>(provider: P, shutdown: CancellationToken) -> oneshot::Receiver<()>
+    where
+        P: Provider + 'static,
+    {
+        let (keepalive_failed_tx, keepalive_failed_rx) = oneshot::channel::<()>();
+        tokio::spawn(async move {
+            let mut keepalive_timer = interval(KEEPALIVE_INTERVAL);
+            loop {
+                tokio::select! {
+                    () = shutdown.cancelled() => {
+                        debug!("Keep-alive task stopping due to shutdown");
+                        return;
+                    }
+                    _ = keepalive_timer.tick() => {
+                        if let Err(e) = provider.get_chain_id().await {
+                            warn!(error = ?e, "Keep-alive ping failed");
+                            // Signal failure to main loop (ignore send error if receiver dropped)
+                            let _ = keepalive_failed_tx.send(());
+                            return;
+                        }
+                        debug!("Keep-alive ping sent");
+                    }
+                }
+            }
+        });
+        keepalive_failed_rx
+    }
+
     /// Dispatch a single log to the event router.
     async fn dispatch_log<P>(&self, provider: &P, log: Log) -> Result<()>
     where
@@ -455,7 +531,7 @@ impl RealtimeProcessor {
 
         // Send to the event router
         self.log_sender
-            .send((log, meta))
+            .send(Ingest::Log(log, meta))
             .await
             .map_err(|e| InfraError::Internal(format!("Log channel closed: {e}")))?;
 
@@ -509,7 +585,7 @@ impl RealtimeProcessor {
             result.timestamp()
         };
 
-        Ok(EventMetadata {
+        let mut meta = EventMetadata {
             block_number,
             block_hash,
             tx_hash,
@@ -517,7 +593,14 @@ impl RealtimeProcessor {
             log_index,
             timestamp,
             contract: log.address(),
-        })
+            chain_id: self.chain_id,
+            tx_function: None,
+            tx_from: None,
+        };
+        if let Some(resolver) = &self.tx_context {
+            resolver.enrich(&mut meta).await;
+        }
+        Ok(meta)
     }
 
     /// Fetch block timestamp from the RPC provider.
@@ -564,6 +647,7 @@ impl RealtimeProcessor {
 // ═══════════════════════════════════════════════════════════════════════════════
 
 #[cfg(test)]
+#[allow(clippy::expect_used)]
 mod tests {
     use super::*;
 
@@ -587,6 +671,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::assertions_on_constants)]
     fn block_cache_constants_are_reasonable() {
         // Cache should be large enough for high-throughput indexing
         assert!(BLOCK_CACHE_MAX_CAPACITY >= 1000);
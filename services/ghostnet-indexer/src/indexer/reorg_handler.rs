@@ -395,8 +395,10 @@ where
 // ═══════════════════════════════════════════════════════════════════════════════
 
 #[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::significant_drop_tightening)]
 mod tests {
     use super::*;
+    use crate::indexer::Contract;
     use std::collections::HashMap;
     use std::sync::{Arc, Mutex};
 
@@ -456,6 +458,18 @@ mod tests {
             hashes.retain(|&k, _| k > cutoff);
             Ok((before - hashes.len()) as u64)
         }
+
+        async fn get_cursor(&self, _contract: Contract) -> Result<Option<BlockNumber>> {
+            Ok(None)
+        }
+
+        async fn set_cursor(&self, _contract: Contract, _block: BlockNumber) -> Result<()> {
+            Ok(())
+        }
+
+        async fn min_cursor(&self, _contracts: &[Contract]) -> Result<Option<BlockNumber>> {
+            Ok(None)
+        }
     }
 
     impl MockStateStore {
@@ -472,6 +486,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::assertions_on_constants)]
     fn constants_are_reasonable() {
         assert!(MAX_REORG_DEPTH >= 64, "Should handle moderate reorgs");
         assert!(MAX_REORG_DEPTH <= 1024, "Don't search forever");
@@ -522,6 +537,7 @@ mod tests {
     }
 
     #[tokio::test]
+    #[allow(clippy::panic)]
     async fn check_reorg_detected_when_hashes_differ() {
         let stored_hash = B256::from([0xAA; 32]);
         let incoming_parent = B256::from([0xBB; 32]);
@@ -585,8 +601,7 @@ mod tests {
         handler.execute_rollback(fork_point).await.unwrap();
 
         // Verify rollback was called
-        let called = store.rollback_called.lock().unwrap();
-        assert_eq!(*called, Some(fork_point));
+        assert_eq!(*store.rollback_called.lock().unwrap(), Some(fork_point));
 
         // Verify blocks after fork point are gone
         assert!(
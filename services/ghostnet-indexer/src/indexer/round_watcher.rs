@@ -0,0 +1,643 @@
+//! Derived DeadPool round events.
+//!
+//! The indexer otherwise only emits what happened on-chain, and nothing
+//! happens on-chain as a round's deadline approaches. The [`RoundWatcher`]
+//! scans the active rounds every `poll_interval` and publishes
+//! [`DerivedEvent`]s to the `derived` topic:
+//!
+//! - `RoundClosingSoon` as the deadline comes within each configured
+//!   threshold (e.g. 1h, 10m, 1m)
+//! - `RoundAwaitingResolution` once the deadline passed and no
+//!   `RoundResolved` was indexed
+//!
+//! ```text
+//! ticker ──▶ RoundWatcher ──▶ MarketStore::get_active_rounds
+//!                 │
+//!                 └──▶ publish_acknowledged("derived", message ID per round + threshold)
+//! ```
+//!
+//! The events are published in the [wire schemas](crate::streaming::wire)
+//! of the `derived` topic.
+//!
+//! # Delivery
+//!
+//! Each event is published at most once per round and threshold: the watcher
+//! remembers what it published, and each event's message ID is derived from
+//! its round and threshold, so an event derived again after a restart is
+//! dropped as a redelivery. When a scan skips past several thresholds (a
+//! round created 5 minutes before its deadline, or a long outage), only the
+//! tightest one is emitted. Resolved rounds are no longer active, so they get
+//! no further events.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use alloy::primitives::U256;
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, instrument, warn};
+
+use crate::config::RoundWatcherSettings;
+use crate::error::Result;
+use crate::ports::{Clock, EventPublisher, MarketStore, SystemClock};
+use crate::streaming::{Topic, WireSchemas};
+use crate::types::entities::Round;
+use crate::types::events::{DerivedEvent, RoundAwaitingResolutionEvent, RoundClosingSoonEvent};
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// ROUND WATCHER
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Publishes derived events for the active DeadPool rounds.
+///
+/// Run one watcher per indexer.
+#[derive(Debug)]
+pub struct RoundWatcher<S, P, K = SystemClock> {
+    /// Store the active rounds are read from.
+    store: Arc<S>,
+    /// Publisher the derived events are sent through.
+    publisher: Arc<P>,
+    /// Time source the deadlines are compared with.
+    clock: Arc<K>,
+    /// `RoundClosingSoon` thresholds in seconds, descending.
+    thresholds: Vec<u64>,
+    /// Interval between scans.
+    poll_interval: Duration,
+    /// Most active rounds scanned per poll.
+    max_rounds: u32,
+    /// What was published per active round, by on-chain round ID.
+    published: Mutex<HashMap<String, Published>>,
+    /// Schema versions the events are published in.
+    schemas: Arc<WireSchemas>,
+}
+
+/// Derived events already published for a round.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Published {
+    /// Tightest `RoundClosingSoon` threshold published (or skipped).
+    closing_soon: Option<u64>,
+    /// Whether `RoundAwaitingResolution` was published.
+    awaiting_resolution: bool,
+}
+
+impl<S: MarketStore, P: EventPublisher> RoundWatcher<S, P> {
+    /// Create a new watcher.
+    #[must_use]
+    pub fn new(store: Arc<S>, publisher: Arc<P>, settings: &RoundWatcherSettings) -> Self {
+        Self::with_clock(store, publisher, Arc::new(SystemClock), settings)
+    }
+}
+
+impl<S, P, K> RoundWatcher<S, P, K>
+where
+    S: MarketStore,
+    P: EventPublisher,
+    K: Clock,
+{
+    /// Create a new watcher with a custom clock.
+    #[must_use]
+    pub fn with_clock(
+        store: Arc<S>,
+        publisher: Arc<P>,
+        clock: Arc<K>,
+        settings: &RoundWatcherSettings,
+    ) -> Self {
+        let mut thresholds = settings.closing_soon_secs.clone();
+        thresholds.sort_unstable_by(|a, b| b.cmp(a));
+        thresholds.dedup();
+        Self {
+            store,
+            publisher,
+            clock,
+            thresholds,
+            poll_interval: settings.poll_interval(),
+            max_rounds: settings.max_rounds.max(1),
+            published: Mutex::new(HashMap::new()),
+            schemas: Arc::default(),
+        }
+    }
+
+    /// Publish the schema versions of `schemas` instead of all of them.
+    #[must_use]
+    pub fn with_schemas(mut self, schemas: Arc<WireSchemas>) -> Self {
+        self.schemas = schemas;
+        self
+    }
+
+    /// Scan the active rounds once, returning the derived events published.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the rounds cannot be read or the events cannot be
+    /// published. Nothing is remembered as published then, so the next scan
+    /// derives the events again.
+    #[instrument(skip(self))]
+    pub async fn scan(&self) -> Result<Vec<DerivedEvent>> {
+        let now = self.clock.now();
+        let rounds = self.store.get_active_rounds(self.max_rounds).await?;
+        let derived = self.derive(&rounds, now);
+        if derived.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut messages = Vec::with_capacity(derived.len());
+        for (_, _, event) in &derived {
+            let encoded = self.schemas.encode_derived(event)?;
+            messages.extend(
+                encoded
+                    .into_iter()
+                    .map(|message| message.identified(event.message_id())),
+            );
+        }
+        self.publisher
+            .publish_acknowledged(Topic::Derived.as_str(), &messages)
+            .await?;
+
+        let mut published = self.published.lock();
+        let events = derived
+            .into_iter()
+            .map(|(round_id, marker, event)| {
+                published.insert(round_id, marker);
+                event
+            })
+            .collect::<Vec<_>>();
+        drop(published);
+        debug!(count = events.len(), "Published derived round events");
+        Ok(events)
+    }
+
+    /// Derive the events due for `rounds` at `now`, with what each round's
+    /// published marker becomes once they are published.
+    ///
+    /// Forgets the rounds that are no longer active.
+    fn derive(
+        &self,
+        rounds: &[Round],
+        now: DateTime<Utc>,
+    ) -> Vec<(String, Published, DerivedEvent)> {
+        let mut published = self.published.lock();
+        published.retain(|round_id, _| rounds.iter().any(|round| &round.round_id == round_id));
+
+        let derived = rounds
+            .iter()
+            .filter(|round| !round.is_resolved)
+            .filter_map(|round| {
+                let Ok(round_id) = round.round_id.parse::<U256>() else {
+                    warn!(round_id = %round.round_id, "Skipping round with invalid ID");
+                    return None;
+                };
+                let marker = published.get(&round.round_id).copied().unwrap_or_default();
+                let (marker, event) = self.next_event(round, round_id, marker, now)?;
+                Some((round.round_id.clone(), marker, event))
+            })
+            .collect();
+        drop(published);
+        derived
+    }
+
+    /// The next event due for `round`, if any, and its updated marker.
+    fn next_event(
+        &self,
+        round: &Round,
+        round_id: U256,
+        mut marker: Published,
+        now: DateTime<Utc>,
+    ) -> Option<(Published, DerivedEvent)> {
+        let remaining = (round.deadline - now).num_seconds();
+
+        if remaining <= 0 {
+            if marker.awaiting_resolution {
+                return None;
+            }
+            marker.awaiting_resolution = true;
+            marker.closing_soon = self.thresholds.last().copied();
+            let event = DerivedEvent::RoundAwaitingResolution(RoundAwaitingResolutionEvent {
+                round_id,
+                deadline: round.deadline,
+                seconds_overdue: remaining.unsigned_abs(),
+                derived_at: now,
+            });
+            return Some((marker, event));
+        }
+
+        // The tightest threshold crossed, unless it was already published
+        let remaining = remaining.unsigned_abs();
+        let threshold = self
+            .thresholds
+            .iter()
+            .rev()
+            .copied()
+            .find(|threshold| remaining <= *threshold)?;
+        if marker
+            .closing_soon
+            .is_some_and(|published| published <= threshold)
+        {
+            return None;
+        }
+        marker.closing_soon = Some(threshold);
+        let event = DerivedEvent::RoundClosingSoon(RoundClosingSoonEvent {
+            round_id,
+            threshold_secs: threshold,
+            seconds_remaining: remaining,
+            deadline: round.deadline,
+            derived_at: now,
+        });
+        Some((marker, event))
+    }
+
+    /// Spawn the background scan task.
+    ///
+    /// Scans immediately, then every `poll_interval` until `shutdown` is
+    /// cancelled.
+    pub fn spawn_watch_task(self: &Arc<Self>, shutdown: CancellationToken) -> JoinHandle<()>
+    where
+        S: 'static,
+        P: 'static,
+        K: 'static,
+    {
+        let watcher = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(watcher.poll_interval);
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+            loop {
+                tokio::select! {
+                    () = shutdown.cancelled() => break,
+                    _ = ticker.tick() => {}
+                }
+                if let Err(e) = watcher.scan().await {
+                    warn!(error = %e, "Round watcher scan failed");
+                }
+            }
+            info!("Round watcher stopped");
+        })
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::significant_drop_tightening)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use async_trait::async_trait;
+    use chrono::{TimeDelta, TimeZone};
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::error::InfraError;
+    use crate::ports::{FakeClock, IdentifiedMessage};
+    use crate::streaming::Envelope;
+    use crate::streaming::wire::DerivedEventV1;
+    use crate::types::entities::{Bet, RoundSettlement};
+    use crate::types::enums::RoundType;
+    use crate::types::primitives::{EthAddress, TokenAmount};
+
+    /// Store holding rounds by on-chain ID.
+    #[derive(Debug, Default)]
+    struct MemoryRounds {
+        rounds: Mutex<Vec<Round>>,
+    }
+
+    impl MemoryRounds {
+        fn add(&self, round_id: &str, deadline: DateTime<Utc>) {
+            self.rounds.lock().push(Round {
+                id: Uuid::new_v4(),
+                round_id: round_id.into(),
+                round_type: RoundType::DeathCount,
+                target_level: None,
+                line: TokenAmount::zero(),
+                deadline,
+                over_pool: TokenAmount::zero(),
+                under_pool: TokenAmount::zero(),
+                is_resolved: false,
+                outcome: None,
+                resolve_time: None,
+                total_burned: None,
+                payout_multiple_bps: None,
+                house_take: None,
+            });
+        }
+    }
+
+    #[async_trait]
+    impl MarketStore for MemoryRounds {
+        async fn save_round(&self, round: &Round) -> Result<()> {
+            self.rounds.lock().push(round.clone());
+            Ok(())
+        }
+
+        async fn record_bet(&self, _bet: &Bet) -> Result<()> {
+            Ok(())
+        }
+
+        async fn resolve_round(
+            &self,
+            round_id: &str,
+            settlement: &RoundSettlement,
+        ) -> Result<()> {
+            let mut rounds = self.rounds.lock();
+            let round = rounds
+                .iter_mut()
+                .find(|round| round.round_id == round_id)
+                .ok_or(InfraError::NotFound)?;
+            round.is_resolved = true;
+            round.outcome = Some(settlement.outcome);
+            round.total_burned = Some(settlement.burned.clone());
+            Ok(())
+        }
+
+        async fn get_active_rounds(&self, limit: u32) -> Result<Vec<Round>> {
+            let rounds = self.rounds.lock();
+            Ok(rounds
+                .iter()
+                .filter(|round| !round.is_resolved)
+                .take(limit as usize)
+                .cloned()
+                .collect())
+        }
+
+        async fn get_round_by_id(&self, round_id: &str) -> Result<Option<Round>> {
+            let rounds = self.rounds.lock();
+            Ok(rounds
+                .iter()
+                .find(|round| round.round_id == round_id)
+                .cloned())
+        }
+
+        async fn get_bets_for_round(&self, _round_id: &str) -> Result<Vec<Bet>> {
+            Ok(Vec::new())
+        }
+
+        async fn get_user_bets(&self, _address: &EthAddress, _limit: u32) -> Result<Vec<Bet>> {
+            Ok(Vec::new())
+        }
+
+        async fn mark_bet_claimed(
+            &self,
+            _round_id: &str,
+            _user: &EthAddress,
+            _winnings: &TokenAmount,
+        ) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Publisher recording delivered messages, optionally failing.
+    #[derive(Debug, Default)]
+    struct RecordingPublisher {
+        /// Delivered messages as (topic, ID, payload), in delivery order.
+        delivered: Mutex<Vec<(String, u128, Vec<u8>)>>,
+        /// Remaining failures.
+        failures: AtomicU32,
+    }
+
+    impl RecordingPublisher {
+        fn events(&self) -> Vec<DerivedEventV1> {
+            let delivered = self.delivered.lock();
+            delivered
+                .iter()
+                .map(|(_, _, payload)| {
+                    serde_json::from_slice::<Envelope<DerivedEventV1>>(payload)
+                        .unwrap()
+                        .event
+                })
+                .collect()
+        }
+    }
+
+    #[async_trait]
+    impl EventPublisher for RecordingPublisher {
+        async fn publish(&self, _event: &crate::types::events::GhostnetEvent) -> Result<()> {
+            Ok(())
+        }
+
+        async fn publish_to_topic(&self, _topic: &str, _payload: &[u8]) -> Result<()> {
+            Ok(())
+        }
+
+        async fn publish_batch(
+            &self,
+            _events: &[crate::types::events::GhostnetEvent],
+        ) -> Result<()> {
+            Ok(())
+        }
+
+        async fn publish_acknowledged(
+            &self,
+            topic: &str,
+            messages: &[IdentifiedMessage],
+        ) -> Result<()> {
+            if self
+                .failures
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                .is_ok()
+            {
+                return Err(InfraError::Streaming("injected failure".into()).into());
+            }
+            let mut delivered = self.delivered.lock();
+            delivered.extend(
+                messages
+                    .iter()
+                    .map(|m| (topic.to_string(), m.id, m.payload.to_vec())),
+            );
+            Ok(())
+        }
+
+        async fn flush(&self) -> Result<()> {
+            Ok(())
+        }
+
+        fn is_connected(&self) -> bool {
+            true
+        }
+    }
+
+    type Watcher = RoundWatcher<MemoryRounds, RecordingPublisher, FakeClock>;
+
+    fn start() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 3, 1, 12, 0, 0).unwrap()
+    }
+
+    fn watcher() -> (
+        Watcher,
+        Arc<MemoryRounds>,
+        Arc<RecordingPublisher>,
+        Arc<FakeClock>,
+    ) {
+        let store = Arc::new(MemoryRounds::default());
+        let publisher = Arc::new(RecordingPublisher::default());
+        let clock = Arc::new(FakeClock::new(start()));
+        let watcher = RoundWatcher::with_clock(
+            Arc::clone(&store),
+            Arc::clone(&publisher),
+            Arc::clone(&clock),
+            &RoundWatcherSettings::default(),
+        );
+        (watcher, store, publisher, clock)
+    }
+
+    /// Thresholds of the `RoundClosingSoon` events, and whether a
+    /// `RoundAwaitingResolution` followed.
+    fn summary(events: &[DerivedEvent]) -> (Vec<u64>, bool) {
+        let thresholds = events
+            .iter()
+            .filter_map(|event| match event {
+                DerivedEvent::RoundClosingSoon(e) => Some(e.threshold_secs),
+                _ => None,
+            })
+            .collect();
+        let awaiting = events
+            .iter()
+            .any(|event| matches!(event, DerivedEvent::RoundAwaitingResolution(_)));
+        (thresholds, awaiting)
+    }
+
+    #[tokio::test]
+    #[allow(clippy::panic)]
+    async fn emits_each_threshold_once_then_awaits_resolution() {
+        let (watcher, store, publisher, clock) = watcher();
+        store.add("7", start() + TimeDelta::hours(2));
+
+        // Two hours out, nothing is due
+        assert!(watcher.scan().await.unwrap().is_empty());
+
+        for (advance, threshold) in [(61, 3600), (50, 600), (8, 60)] {
+            clock.advance(TimeDelta::minutes(advance));
+            let events = watcher.scan().await.unwrap();
+            assert_eq!(summary(&events), (vec![threshold], false));
+            // Scanning again before the next threshold emits nothing
+            assert!(watcher.scan().await.unwrap().is_empty());
+        }
+
+        clock.advance(TimeDelta::seconds(90));
+        let events = watcher.scan().await.unwrap();
+        let [DerivedEvent::RoundAwaitingResolution(awaiting)] = events.as_slice() else {
+            panic!("expected RoundAwaitingResolution, got {events:?}");
+        };
+        assert_eq!(awaiting.round_id, U256::from(7));
+        assert_eq!(awaiting.seconds_overdue, 30);
+
+        clock.advance(TimeDelta::hours(1));
+        assert!(watcher.scan().await.unwrap().is_empty());
+
+        // Everything went to the derived topic, once
+        let delivered = publisher.delivered.lock().clone();
+        assert_eq!(delivered.len(), 4);
+        assert!(delivered.iter().all(|(topic, _, _)| topic == "derived"));
+        let thresholds: Vec<u64> = publisher
+            .events()
+            .into_iter()
+            .filter_map(|event| match event {
+                DerivedEventV1::RoundClosingSoon { threshold_secs, .. } => Some(threshold_secs),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(thresholds, [3600, 600, 60]);
+    }
+
+    #[tokio::test]
+    async fn round_resolved_before_the_last_threshold_gets_no_more_events() {
+        let (watcher, store, publisher, clock) = watcher();
+        store.add("1", start() + TimeDelta::minutes(30));
+        store.add("2", start() + TimeDelta::minutes(30));
+
+        let events = watcher.scan().await.unwrap();
+        assert_eq!(summary(&events), (vec![3600, 3600], false));
+
+        clock.advance(TimeDelta::minutes(25));
+        let round = store.get_round_by_id("1").await.unwrap().unwrap();
+        let settlement = round.settle(true, &TokenAmount::zero(), &TokenAmount::zero(), start());
+        store.resolve_round("1", &settlement).await.unwrap();
+        let events = watcher.scan().await.unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].aggregate(), "round:2");
+
+        clock.advance(TimeDelta::minutes(10));
+        let events = watcher.scan().await.unwrap();
+        assert_eq!(summary(&events), (vec![], true));
+        let round_1 = publisher
+            .events()
+            .into_iter()
+            .filter(|event| match event {
+                DerivedEventV1::RoundClosingSoon { round_id, .. }
+                | DerivedEventV1::RoundAwaitingResolution { round_id, .. } => round_id == "1",
+                DerivedEventV1::BoostExpired { .. } => false,
+            })
+            .count();
+        assert_eq!(round_1, 1, "round 1 only got its first threshold");
+    }
+
+    #[tokio::test]
+    #[allow(clippy::panic)]
+    async fn skipped_thresholds_emit_only_the_tightest() {
+        let (watcher, store, _, clock) = watcher();
+        store.add("3", start() + TimeDelta::hours(2));
+
+        // An outage straight past the 1h and 10m marks
+        clock.advance(TimeDelta::minutes(115));
+        let events = watcher.scan().await.unwrap();
+        let [DerivedEvent::RoundClosingSoon(closing)] = events.as_slice() else {
+            panic!("expected RoundClosingSoon, got {events:?}");
+        };
+        assert_eq!(
+            (closing.threshold_secs, closing.seconds_remaining),
+            (600, 300)
+        );
+
+        clock.advance(TimeDelta::minutes(1));
+        assert!(watcher.scan().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn failed_publish_is_derived_again() {
+        let (watcher, store, publisher, _) = watcher();
+        store.add("4", start() + TimeDelta::minutes(5));
+        publisher.failures.store(1, Ordering::SeqCst);
+
+        assert!(watcher.scan().await.is_err());
+        let events = watcher.scan().await.unwrap();
+        assert_eq!(summary(&events), (vec![600], false));
+        assert!(watcher.scan().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn restarted_watcher_derives_the_same_message_ids() {
+        let (watcher, store, publisher, clock) = watcher();
+        store.add("5", start() + TimeDelta::minutes(5));
+        watcher.scan().await.unwrap();
+
+        // A new watcher does not know what was published, but the
+        // redelivery carries the same ID
+        let restarted = RoundWatcher::with_clock(
+            Arc::clone(&store),
+            Arc::clone(&publisher),
+            clock,
+            &RoundWatcherSettings::default(),
+        );
+        restarted.scan().await.unwrap();
+
+        let delivered = publisher.delivered.lock().clone();
+        assert_eq!(delivered.len(), 2);
+        assert_eq!(delivered[0].1, delivered[1].1);
+        assert_ne!(
+            delivered[0].1,
+            DerivedEvent::RoundClosingSoon(RoundClosingSoonEvent {
+                round_id: U256::from(5),
+                threshold_secs: 60,
+                seconds_remaining: 0,
+                deadline: start(),
+                derived_at: start(),
+            })
+            .message_id(),
+            "IDs differ per threshold"
+        );
+    }
+}
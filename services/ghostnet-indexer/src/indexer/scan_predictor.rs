@@ -0,0 +1,519 @@
+//! Next-scan prediction.
+//!
+//! Every level with scans has a cadence set by `GhostCore`, but keepers run
+//! the scans, so the actual times drift. [`ScanPredictor`] answers "when is
+//! the next scan?" per level:
+//!
+//! 1. With a contract configured, from `GhostCore.getLevelState(level)`,
+//!    whose `nextScanTime` is authoritative.
+//! 2. Otherwise, or when the call fails, from the last scans in the
+//!    [`ScanStore`]: the next scan is expected one median interval after
+//!    the last one, between its 25th and 75th percentile intervals.
+//!
+//! ```text
+//! GET /scans/next ──▶ ScanPredictor ──▶ cache (short TTL)
+//!                          │ miss
+//!                          ├──▶ GhostCore.getLevelState   (preferred)
+//!                          └──▶ ScanStore::get_recent_scans (fallback)
+//!
+//! ScanHandler (ScanExecuted) ──▶ ScanPredictor::invalidate(level)
+//! ```
+
+use std::sync::Arc;
+
+use alloy::primitives::Address;
+use alloy::providers::{DynProvider, Provider};
+use alloy::rpc::types::TransactionRequest;
+use alloy::sol_types::SolCall;
+use chrono::{DateTime, TimeDelta, TimeZone, Utc};
+use moka::sync::Cache as MokaCache;
+use tracing::{debug, warn};
+
+use crate::abi::ghost_core;
+use crate::config::ScanPredictionSettings;
+use crate::error::{InfraError, Result};
+use crate::ports::ScanStore;
+use crate::types::entities::{PredictionSource, Scan, ScanCadence, ScanPrediction};
+use crate::types::enums::Level;
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// SCAN PREDICTOR
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Predicts the next scan of each level.
+pub struct ScanPredictor<S> {
+    /// Store the scan history is read from.
+    store: Arc<S>,
+    /// Provider and `GhostCore` address for the scheduled next scan time.
+    contract: Option<(DynProvider, Address)>,
+    /// Predictions by level; `None` caches "not enough data".
+    cache: MokaCache<Level, Option<ScanPrediction>>,
+    /// Recent scans per level the cadence is inferred from.
+    history: u32,
+}
+
+impl<S> std::fmt::Debug for ScanPredictor<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ScanPredictor")
+            .field(
+                "contract",
+                &self.contract.as_ref().map(|(_, address)| address),
+            )
+            .field("history", &self.history)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S: ScanStore> ScanPredictor<S> {
+    /// Create a predictor working from the scan history in `store`.
+    #[must_use]
+    pub fn new(store: Arc<S>, settings: &ScanPredictionSettings) -> Self {
+        Self {
+            store,
+            contract: None,
+            cache: MokaCache::builder()
+                .max_capacity(Level::all_valid().len() as u64)
+                .time_to_live(settings.cache_ttl())
+                .build(),
+            history: settings.history.max(2),
+        }
+    }
+
+    /// Prefer the next scan time scheduled by `GhostCore` at `ghost_core`.
+    #[must_use]
+    pub fn with_contract<P>(mut self, provider: P, ghost_core: Address) -> Self
+    where
+        P: Provider + 'static,
+    {
+        self.contract = Some((provider.erased(), ghost_core));
+        self
+    }
+
+    /// Predict the next scan of `level`.
+    ///
+    /// Returns `None` for levels without scans, and for levels without a
+    /// scheduled time that have fewer than two scans. Results are cached
+    /// until their TTL passes or [`Self::invalidate`] is called.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the scan history cannot be read.
+    pub async fn predict(&self, level: Level) -> Result<Option<ScanPrediction>> {
+        if !level.has_scans() {
+            return Ok(None);
+        }
+        if let Some(prediction) = self.cache.get(&level) {
+            return Ok(prediction);
+        }
+
+        let scans = self.store.get_recent_scans(level, self.history + 1).await?;
+        let cadence = cadence(&scans);
+        let prediction = match self.scheduled(level).await {
+            Some(next_scan_at) => Some(ScanPrediction {
+                level,
+                source: PredictionSource::Contract,
+                next_scan_at,
+                earliest_at: next_scan_at,
+                latest_at: next_scan_at,
+                cadence,
+            }),
+            None => cadence.map(|cadence| from_cadence(level, cadence)),
+        };
+
+        debug!(?level, ?prediction, "Predicted next scan");
+        self.cache.insert(level, prediction.clone());
+        Ok(prediction)
+    }
+
+    /// Predict the next scan of every level with scans.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the scan history of a level cannot be read.
+    pub async fn predict_all(&self) -> Result<Vec<ScanPrediction>> {
+        let mut predictions = Vec::new();
+        for level in Level::all_valid() {
+            predictions.extend(self.predict(level).await?);
+        }
+        Ok(predictions)
+    }
+
+    /// Drop the cached prediction of `level`, e.g. after it was scanned.
+    pub fn invalidate(&self, level: Level) {
+        self.cache.invalidate(&level);
+        debug!(?level, "Invalidated scan prediction");
+    }
+
+    /// The next scan time scheduled on-chain, if a contract is configured
+    /// and reports one.
+    async fn scheduled(&self, level: Level) -> Option<DateTime<Utc>> {
+        let (provider, ghost_core) = self.contract.as_ref()?;
+        match next_scan_time(provider, *ghost_core, level).await {
+            Ok(time) => time,
+            Err(e) => {
+                warn!(?level, error = %e, "Failed to read next scan time, using scan history");
+                None
+            }
+        }
+    }
+}
+
+/// Read `nextScanTime` of `level` from `GhostCore`; `None` if it is unset.
+async fn next_scan_time(
+    provider: &DynProvider,
+    ghost_core: Address,
+    level: Level,
+) -> Result<Option<DateTime<Utc>>> {
+    let call = ghost_core::getLevelStateCall {
+        level: u8::from(level),
+    };
+    let request = TransactionRequest::default()
+        .to(ghost_core)
+        .input(call.abi_encode().into());
+    let output = provider
+        .call(request)
+        .await
+        .map_err(|e| InfraError::Rpc(Box::new(e)))?;
+    let state = ghost_core::getLevelStateCall::abi_decode_returns(&output)
+        .map_err(|e| InfraError::EventDecoding(format!("getLevelState: {e}")))?;
+
+    Ok(i64::try_from(state.nextScanTime)
+        .ok()
+        .filter(|&time| time > 0)
+        .and_then(|time| Utc.timestamp_opt(time, 0).single()))
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// CADENCE
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Cadence of `scans`, in any order; `None` with fewer than two of them.
+///
+/// Placeholder scans are left out, since their execution time is when a
+/// later event was seen rather than when they ran.
+fn cadence(scans: &[Scan]) -> Option<ScanCadence> {
+    let mut times: Vec<_> = scans
+        .iter()
+        .filter(|scan| !scan.is_placeholder())
+        .map(|scan| scan.executed_at)
+        .collect();
+    times.sort_unstable();
+
+    let mut intervals: Vec<_> = times
+        .windows(2)
+        .map(|pair| (pair[1] - pair[0]).num_seconds())
+        .filter(|&secs| secs > 0)
+        .collect();
+    if intervals.is_empty() {
+        return None;
+    }
+    intervals.sort_unstable();
+
+    Some(ScanCadence {
+        last_scan_at: *times.last()?,
+        intervals: u32::try_from(intervals.len()).unwrap_or(u32::MAX),
+        median_interval_secs: percentile(&intervals, 50),
+        p25_interval_secs: percentile(&intervals, 25),
+        p75_interval_secs: percentile(&intervals, 75),
+    })
+}
+
+/// Predict the next scan from the cadence alone.
+fn from_cadence(level: Level, cadence: ScanCadence) -> ScanPrediction {
+    let after = |secs| cadence.last_scan_at + TimeDelta::seconds(secs);
+    ScanPrediction {
+        level,
+        source: PredictionSource::History,
+        next_scan_at: after(cadence.median_interval_secs),
+        earliest_at: after(cadence.p25_interval_secs),
+        latest_at: after(cadence.p75_interval_secs),
+        cadence: Some(cadence),
+    }
+}
+
+/// The `pct`th percentile of non-empty `sorted`, interpolated linearly and
+/// rounded down.
+fn percentile(sorted: &[i64], pct: usize) -> i64 {
+    let rank = (sorted.len() - 1) * pct;
+    let (index, fraction) = (rank / 100, rank % 100);
+    let low = sorted[index];
+    let high = sorted.get(index + 1).copied().unwrap_or(low);
+    low + (high - low) * i64::try_from(fraction).unwrap_or(0) / 100
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use std::sync::Mutex;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use alloy::primitives::{Bytes, U256};
+    use alloy::providers::ProviderBuilder;
+    use alloy::transports::mock::Asserter;
+    use async_trait::async_trait;
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::types::entities::ScanFinalizationData;
+
+    fn start() -> DateTime<Utc> {
+        Utc.timestamp_opt(1_700_000_000, 0).unwrap()
+    }
+
+    /// Executed scans of `level` separated by `intervals` seconds.
+    fn history(level: Level, intervals: &[i64]) -> Vec<Scan> {
+        let mut executed_at = start();
+        let mut scans = vec![scan(level, executed_at)];
+        for &secs in intervals {
+            executed_at += TimeDelta::seconds(secs);
+            scans.push(scan(level, executed_at));
+        }
+        scans
+    }
+
+    fn scan(level: Level, executed_at: DateTime<Utc>) -> Scan {
+        Scan {
+            seed: "42".into(),
+            ..Scan::placeholder(Uuid::new_v4().to_string(), level, executed_at)
+        }
+    }
+
+    /// Store serving a fixed scan history, newest first.
+    #[derive(Debug, Default)]
+    struct HistoryStore {
+        scans: Mutex<Vec<Scan>>,
+        queries: AtomicU32,
+    }
+
+    impl HistoryStore {
+        fn new(scans: Vec<Scan>) -> Arc<Self> {
+            Arc::new(Self {
+                scans: Mutex::new(scans),
+                queries: AtomicU32::new(0),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl ScanStore for HistoryStore {
+        async fn save_scan(&self, scan: &Scan) -> Result<()> {
+            self.scans.lock().unwrap().push(scan.clone());
+            Ok(())
+        }
+
+        async fn finalize_scan(&self, _: &str, _: ScanFinalizationData) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_recent_scans(&self, level: Level, limit: u32) -> Result<Vec<Scan>> {
+            self.queries.fetch_add(1, Ordering::Relaxed);
+            let mut scans: Vec<_> = self
+                .scans
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|scan| scan.level == level)
+                .cloned()
+                .collect();
+            scans.sort_by_key(|scan| std::cmp::Reverse(scan.executed_at));
+            scans.truncate(limit as usize);
+            Ok(scans)
+        }
+
+        async fn get_scan_by_id(&self, _: &str) -> Result<Option<Scan>> {
+            Ok(None)
+        }
+
+        async fn get_pending_scans(&self) -> Result<Vec<Scan>> {
+            Ok(vec![])
+        }
+
+        async fn link_deaths_to_scan(&self, _: &str, _: &[Uuid]) -> Result<u64> {
+            Ok(0)
+        }
+    }
+
+    fn predictor(store: &Arc<HistoryStore>) -> ScanPredictor<HistoryStore> {
+        ScanPredictor::new(Arc::clone(store), &ScanPredictionSettings::default())
+    }
+
+    /// ABI-encoded `getLevelState` result with the given `nextScanTime`.
+    fn level_state(next_scan_time: u64) -> Bytes {
+        ghost_core::getLevelStateCall::abi_encode_returns(&ghost_core::LevelState {
+            totalStaked: U256::ZERO,
+            aliveCount: U256::ZERO,
+            accRewardsPerShare: U256::ZERO,
+            nextScanTime: next_scan_time,
+        })
+        .into()
+    }
+
+    #[tokio::test]
+    async fn regular_cadence_has_a_narrow_band() {
+        let store = HistoryStore::new(history(Level::Darknet, &[7200; 6]));
+        let last = start() + TimeDelta::hours(12);
+
+        let prediction = predictor(&store)
+            .predict(Level::Darknet)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(prediction.source, PredictionSource::History);
+        assert_eq!(prediction.next_scan_at, last + TimeDelta::hours(2));
+        assert_eq!(prediction.earliest_at, prediction.next_scan_at);
+        assert_eq!(prediction.latest_at, prediction.next_scan_at);
+        let cadence = prediction.cadence.unwrap();
+        assert_eq!((cadence.last_scan_at, cadence.intervals), (last, 6));
+        assert_eq!(cadence.median_interval_secs, 7200);
+    }
+
+    #[tokio::test]
+    async fn drifting_cadence_widens_the_band() {
+        // Keepers fall further behind: 60s, 70s, ... 150s apart
+        let intervals: Vec<_> = (6..=15).map(|tens| tens * 10).collect();
+        let store = HistoryStore::new(history(Level::BlackIce, &intervals));
+        let last = start() + TimeDelta::seconds(intervals.iter().sum());
+
+        let prediction = predictor(&store)
+            .predict(Level::BlackIce)
+            .await
+            .unwrap()
+            .unwrap();
+
+        let cadence = prediction.cadence.clone().unwrap();
+        assert_eq!(cadence.intervals, 10);
+        assert_eq!(
+            (
+                cadence.p25_interval_secs,
+                cadence.median_interval_secs,
+                cadence.p75_interval_secs
+            ),
+            (82, 105, 127)
+        );
+        assert_eq!(prediction.next_scan_at, last + TimeDelta::seconds(105));
+        assert_eq!(prediction.earliest_at, last + TimeDelta::seconds(82));
+        assert_eq!(prediction.latest_at, last + TimeDelta::seconds(127));
+    }
+
+    #[tokio::test]
+    async fn only_recent_scans_and_executed_ones_count() {
+        let mut scans = history(Level::Subnet, &[100; 30]);
+        // A placeholder's time is when a later event was seen
+        let seen_at = start() + TimeDelta::seconds(2950);
+        scans.push(Scan::placeholder("late".into(), Level::Subnet, seen_at));
+        let store = HistoryStore::new(scans);
+
+        let prediction = predictor(&store)
+            .predict(Level::Subnet)
+            .await
+            .unwrap()
+            .unwrap();
+
+        // The last 21 scans, one of them the placeholder
+        let cadence = prediction.cadence.unwrap();
+        assert_eq!((cadence.intervals, cadence.p25_interval_secs), (19, 100));
+    }
+
+    #[tokio::test]
+    async fn single_scan_predicts_nothing() {
+        let store = HistoryStore::new(history(Level::Subnet, &[]));
+        let predictor = predictor(&store);
+
+        assert_eq!(predictor.predict(Level::Subnet).await.unwrap(), None);
+        // Nor do levels that are never scanned, without asking the store
+        assert_eq!(predictor.predict(Level::Vault).await.unwrap(), None);
+        assert_eq!(store.queries.load(Ordering::Relaxed), 1);
+    }
+
+    #[tokio::test]
+    async fn single_interval_predicts_a_point() {
+        let store = HistoryStore::new(history(Level::Subnet, &[28_800]));
+
+        let prediction = predictor(&store)
+            .predict(Level::Subnet)
+            .await
+            .unwrap()
+            .unwrap();
+
+        let next = start() + TimeDelta::seconds(2 * 28_800);
+        assert_eq!(
+            (
+                prediction.earliest_at,
+                prediction.next_scan_at,
+                prediction.latest_at
+            ),
+            (next, next, next)
+        );
+    }
+
+    #[tokio::test]
+    async fn predictions_are_cached_until_invalidated() {
+        let store = HistoryStore::new(history(Level::Darknet, &[7200]));
+        let predictor = predictor(&store);
+
+        let first = predictor.predict(Level::Darknet).await.unwrap();
+        store
+            .save_scan(&scan(Level::Darknet, start() + TimeDelta::hours(3)))
+            .await
+            .unwrap();
+        assert_eq!(predictor.predict(Level::Darknet).await.unwrap(), first);
+        assert_eq!(store.queries.load(Ordering::Relaxed), 1);
+
+        predictor.invalidate(Level::Darknet);
+        let second = predictor.predict(Level::Darknet).await.unwrap().unwrap();
+        assert_eq!(second.cadence.unwrap().intervals, 2);
+        assert_ne!(Some(second.next_scan_at), first.map(|p| p.next_scan_at));
+    }
+
+    #[tokio::test]
+    async fn prefers_the_scheduled_time() {
+        let asserter = Asserter::new();
+        asserter.push_success(&level_state(1_700_100_000));
+        let provider = ProviderBuilder::new().connect_mocked_client(asserter);
+        let store = HistoryStore::new(history(Level::Darknet, &[7200; 3]));
+        let predictor = predictor(&store).with_contract(provider, Address::repeat_byte(1));
+
+        let prediction = predictor.predict(Level::Darknet).await.unwrap().unwrap();
+
+        let scheduled = Utc.timestamp_opt(1_700_100_000, 0).unwrap();
+        assert_eq!(prediction.source, PredictionSource::Contract);
+        assert_eq!(prediction.next_scan_at, scheduled);
+        assert_eq!(prediction.earliest_at, scheduled);
+        assert_eq!(prediction.cadence.unwrap().intervals, 3);
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_history_without_a_scheduled_time() {
+        let asserter = Asserter::new();
+        // Unset on-chain, then a failing node
+        asserter.push_success(&level_state(0));
+        asserter.push_failure_msg("node unavailable");
+        let provider = ProviderBuilder::new().connect_mocked_client(asserter);
+        let store = HistoryStore::new(history(Level::Darknet, &[7200; 3]));
+        let predictor = predictor(&store).with_contract(provider, Address::repeat_byte(1));
+
+        for _ in 0..2 {
+            let prediction = predictor.predict(Level::Darknet).await.unwrap().unwrap();
+            assert_eq!(prediction.source, PredictionSource::History);
+            assert_eq!(prediction.next_scan_at, start() + TimeDelta::hours(8));
+            predictor.invalidate(Level::Darknet);
+        }
+    }
+
+    #[test]
+    fn seconds_remaining_stops_at_zero() {
+        let prediction = from_cadence(
+            Level::Darknet,
+            cadence(&history(Level::Darknet, &[7200])).unwrap(),
+        );
+
+        let now = start() + TimeDelta::hours(3);
+        assert_eq!(prediction.seconds_remaining(now), 3600);
+        assert_eq!(prediction.seconds_remaining(now + TimeDelta::hours(2)), 0);
+    }
+}
@@ -0,0 +1,967 @@
+//! Cross-checking indexed state against the chain.
+//!
+//! Backfills and reorg rollbacks leave no sign of whether the database still
+//! matches the contracts. [`StateVerifier`] reads the authoritative state at
+//! the last indexed block and compares it with what is indexed:
+//!
+//! | Indexed | On chain | Compared |
+//! |---------|----------|----------|
+//! | Active positions | `GhostCore.getPosition` | alive, level, stake, streak |
+//! | Active rounds | `DeadPool.getRound` | resolution, pools |
+//! | Level stats | `GhostCore.getLevelState` | total staked, alive count |
+//! | Global stats | `GhostCore.getTotalValueLocked` | TVL |
+//!
+//! Reads go through Multicall3's `aggregate3`, so thousands of positions
+//! take a handful of `eth_call`s. Positions that only exist on chain are not
+//! found, since the contract cannot be enumerated.
+//!
+//! # Severity
+//!
+//! | Severity | Mismatch |
+//! |----------|----------|
+//! | [`Severity::Critical`] | A closed position or resolved round indexed as open, a wrong level or stake |
+//! | [`Severity::Major`] | A wrong ghost streak or round pool |
+//! | [`Severity::Minor`] | Aggregate stats, which follow from the rows they sum |
+//!
+//! # Fixing
+//!
+//! [`StateVerifier::fix`] overwrites mismatched rows with their on-chain
+//! state and recomputes the aggregate stats from them. Every overwritten
+//! value is returned as a [`JournalEntry`].
+//!
+//! ```ignore
+//! let verifier = StateVerifier::new(store, provider, ghost_core).with_dead_pool(dead_pool);
+//! let report = verifier.verify(Some(500)).await?;
+//! if report.mismatches().len() > threshold {
+//!     let journal = verifier.fix(&report).await?;
+//! }
+//! ```
+
+use std::cmp::Reverse;
+use std::collections::HashSet;
+use std::fmt;
+use std::hash::{BuildHasher, RandomState};
+use std::sync::Arc;
+
+use alloy::primitives::{Address, U256, address};
+use alloy::providers::{DynProvider, Provider};
+use alloy::rpc::types::TransactionRequest;
+use alloy::sol;
+use alloy::sol_types::SolCall;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tracing::{debug, info};
+
+use crate::abi::{dead_pool, ghost_core};
+use crate::error::{InfraError, Result};
+use crate::ports::{IndexerStateStore, MarketStore, PositionStore, StatsStore};
+use crate::types::entities::{GlobalStats, LevelStats, Position, Round};
+use crate::types::enums::{ExitReason, Level};
+use crate::types::primitives::{BlockNumber, EthAddress, GhostStreak, TokenAmount};
+
+/// Canonical Multicall3 deployment address.
+pub const MULTICALL3_ADDRESS: Address = address!("cA11bde05977b3631167028862bE2a173976CA11");
+
+/// Calls per `aggregate3` batch.
+const DEFAULT_BATCH_SIZE: usize = 200;
+
+/// Most active rounds verified in one run.
+const ACTIVE_ROUND_LIMIT: u32 = 1_000;
+
+/// Decimals of the DATA token.
+const DATA_TOKEN_DECIMALS: u8 = 18;
+
+sol! {
+    /// Multicall3 call with per-call failure handling.
+    struct Call3 {
+        address target;
+        bool allowFailure;
+        bytes callData;
+    }
+
+    /// Outcome of a single [`Call3`].
+    struct Result3 {
+        bool success;
+        bytes returnData;
+    }
+
+    function aggregate3(Call3[] calldata calls) external payable returns (Result3[] memory returnData);
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// REPORT
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// How much a mismatch matters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// Aggregate stats, which follow from the rows they sum.
+    Minor,
+    /// A wrong ghost streak or round pool.
+    Major,
+    /// A closed position or resolved round indexed as open, or a wrong
+    /// level or stake.
+    Critical,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Self::Minor => "minor",
+            Self::Major => "major",
+            Self::Critical => "critical",
+        })
+    }
+}
+
+/// The indexed row a mismatch is in.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+#[serde(tag = "kind", content = "id", rename_all = "snake_case")]
+pub enum Subject {
+    /// A user's active position.
+    Position(EthAddress),
+    /// A DeadPool round, by on-chain ID.
+    Round(String),
+    /// A level's aggregate stats.
+    Level(Level),
+    /// The global aggregate stats.
+    Global,
+}
+
+impl fmt::Display for Subject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Position(address) => write!(f, "position {address}"),
+            Self::Round(round_id) => write!(f, "round {round_id}"),
+            Self::Level(level) => write!(f, "level {}", level.name()),
+            Self::Global => f.write_str("global"),
+        }
+    }
+}
+
+/// A field whose indexed value differs from the chain.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Mismatch {
+    /// Row the field is in.
+    pub subject: Subject,
+    /// Name of the field.
+    pub field: &'static str,
+    /// Value in the database.
+    pub indexed: String,
+    /// Value on chain.
+    pub on_chain: String,
+    /// How much the difference matters.
+    pub severity: Severity,
+}
+
+impl Mismatch {
+    fn new(
+        subject: Subject,
+        field: &'static str,
+        indexed: &impl ToString,
+        on_chain: &impl ToString,
+        severity: Severity,
+    ) -> Self {
+        Self {
+            subject,
+            field,
+            indexed: indexed.to_string(),
+            on_chain: on_chain.to_string(),
+            severity,
+        }
+    }
+}
+
+/// A value overwritten by [`StateVerifier::fix`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct JournalEntry {
+    /// When the value was overwritten.
+    pub at: DateTime<Utc>,
+    /// Block the on-chain state was read at.
+    pub block: BlockNumber,
+    /// Row the field is in.
+    pub subject: Subject,
+    /// Name of the field.
+    pub field: &'static str,
+    /// Value before the fix.
+    pub before: String,
+    /// Value after the fix.
+    pub after: String,
+}
+
+/// Outcome of [`StateVerifier::verify`].
+#[derive(Debug, Clone)]
+pub struct VerifyReport {
+    /// Block the on-chain state was read at.
+    pub block: BlockNumber,
+    /// Positions compared.
+    pub positions_checked: usize,
+    /// Rounds compared.
+    pub rounds_checked: usize,
+    /// Checks that could not be made, with the reason.
+    pub skipped: Vec<String>,
+    mismatches: Vec<Mismatch>,
+    /// Rows as they are on chain, for [`StateVerifier::fix`].
+    repairs: Vec<Repair>,
+}
+
+impl VerifyReport {
+    /// Every mismatch found, most severe first.
+    #[must_use]
+    pub fn mismatches(&self) -> &[Mismatch] {
+        &self.mismatches
+    }
+
+    /// Whether nothing differs from the chain.
+    #[must_use]
+    pub const fn is_consistent(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+
+    /// Number of mismatches at `severity` or above.
+    #[must_use]
+    pub fn count_at_least(&self, severity: Severity) -> usize {
+        self.mismatches
+            .iter()
+            .filter(|mismatch| mismatch.severity >= severity)
+            .count()
+    }
+}
+
+/// A row to overwrite with its on-chain state.
+#[derive(Debug, Clone)]
+enum Repair {
+    Position(Box<Position>),
+    Round(Box<Round>),
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// STATE VERIFIER
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Compares indexed state with the contracts, and overwrites what differs.
+pub struct StateVerifier<S> {
+    store: Arc<S>,
+    provider: DynProvider,
+    ghost_core: Address,
+    /// `None` when `DeadPool` is not indexed.
+    dead_pool: Option<Address>,
+    multicall: Address,
+    batch_size: usize,
+}
+
+impl<S> fmt::Debug for StateVerifier<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StateVerifier")
+            .field("ghost_core", &self.ghost_core)
+            .field("dead_pool", &self.dead_pool)
+            .field("multicall", &self.multicall)
+            .field("batch_size", &self.batch_size)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S> StateVerifier<S>
+where
+    S: PositionStore + MarketStore + StatsStore + IndexerStateStore,
+{
+    /// Create a verifier reading `GhostCore` at `ghost_core`.
+    #[must_use]
+    pub fn new<P>(store: Arc<S>, provider: P, ghost_core: Address) -> Self
+    where
+        P: Provider + 'static,
+    {
+        Self {
+            store,
+            provider: provider.erased(),
+            ghost_core,
+            dead_pool: None,
+            multicall: MULTICALL3_ADDRESS,
+            batch_size: DEFAULT_BATCH_SIZE,
+        }
+    }
+
+    /// Also verify the active rounds of `DeadPool` at `dead_pool`.
+    #[must_use]
+    pub const fn with_dead_pool(mut self, dead_pool: Address) -> Self {
+        self.dead_pool = Some(dead_pool);
+        self
+    }
+
+    /// Batch reads through a Multicall3 deployed at `multicall`.
+    #[must_use]
+    pub const fn with_multicall(mut self, multicall: Address) -> Self {
+        self.multicall = multicall;
+        self
+    }
+
+    /// Read at most `batch_size` values per `eth_call`.
+    #[must_use]
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Compare indexed state with the chain at the last indexed block.
+    ///
+    /// With `sample`, only that many active positions, picked at random,
+    /// are compared; rounds and stats are always compared in full.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database cannot be read or an `aggregate3`
+    /// call fails. Rounds that cannot be read are reported as skipped.
+    pub async fn verify(&self, sample: Option<usize>) -> Result<VerifyReport> {
+        let block = self.store.get_last_block().await?;
+        let mut report = VerifyReport {
+            block,
+            positions_checked: 0,
+            rounds_checked: 0,
+            skipped: Vec::new(),
+            mismatches: Vec::new(),
+            repairs: Vec::new(),
+        };
+
+        self.verify_positions(sample, &mut report).await?;
+        self.verify_rounds(&mut report).await?;
+        self.verify_stats(&mut report).await?;
+
+        report
+            .mismatches
+            .sort_by_key(|mismatch| Reverse(mismatch.severity));
+        info!(
+            block = %block,
+            positions = report.positions_checked,
+            rounds = report.rounds_checked,
+            mismatches = report.mismatches.len(),
+            "Verified indexed state"
+        );
+        Ok(report)
+    }
+
+    /// Overwrite the mismatched rows of `report` with their on-chain state.
+    ///
+    /// Level and global stats are recomputed from the positions afterwards.
+    /// Returns one journal entry per overwritten value.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a row cannot be written. Rows written before it
+    /// stay written.
+    pub async fn fix(&self, report: &VerifyReport) -> Result<Vec<JournalEntry>> {
+        let at = Utc::now();
+        let mut repaired = HashSet::new();
+
+        for repair in &report.repairs {
+            match repair {
+                Repair::Position(position) => {
+                    self.store.save_position(position).await?;
+                    repaired.insert(Subject::Position(position.user_address));
+                }
+                Repair::Round(round) => {
+                    self.store.save_round(round).await?;
+                    repaired.insert(Subject::Round(round.round_id.clone()));
+                }
+            }
+        }
+
+        let mut journal: Vec<_> = report
+            .mismatches
+            .iter()
+            .filter(|mismatch| repaired.contains(&mismatch.subject))
+            .map(|mismatch| JournalEntry {
+                at,
+                block: report.block,
+                subject: mismatch.subject.clone(),
+                field: mismatch.field,
+                before: mismatch.indexed.clone(),
+                after: mismatch.on_chain.clone(),
+            })
+            .collect();
+
+        let stats_mismatches: Vec<_> = report
+            .mismatches
+            .iter()
+            .filter(|m| matches!(m.subject, Subject::Level(_) | Subject::Global))
+            .collect();
+        if !repaired.is_empty() || !stats_mismatches.is_empty() {
+            let levels = self.store.recompute_level_stats().await?;
+            let global = self.store.refresh_global_stats().await?;
+            journal.extend(stats_mismatches.into_iter().map(|mismatch| JournalEntry {
+                at,
+                block: report.block,
+                subject: mismatch.subject.clone(),
+                field: mismatch.field,
+                before: mismatch.indexed.clone(),
+                after: recomputed(&levels, &global, &mismatch.subject, mismatch.field),
+            }));
+        }
+
+        info!(
+            rows = repaired.len(),
+            values = journal.len(),
+            "Fixed indexed state"
+        );
+        Ok(journal)
+    }
+
+    // ───────────────────────────────────────────────────────────────────────────
+    // Checks
+    // ───────────────────────────────────────────────────────────────────────────
+
+    async fn verify_positions(
+        &self,
+        sample: Option<usize>,
+        report: &mut VerifyReport,
+    ) -> Result<()> {
+        let mut positions = Vec::new();
+        for level in Level::all_valid() {
+            positions.extend(self.store.get_positions_by_level(level).await?);
+        }
+        if let Some(size) = sample {
+            positions = sample_positions(positions, size);
+        }
+
+        let calls: Vec<_> = positions
+            .iter()
+            .map(|position| ghost_core::getPositionCall {
+                user: Address::from(*position.user_address.as_bytes()),
+            })
+            .collect();
+        let on_chain = self
+            .aggregate(self.ghost_core, &calls, report.block)
+            .await?;
+
+        for (position, chain) in positions.into_iter().zip(on_chain) {
+            let Some(chain) = chain else {
+                report
+                    .skipped
+                    .push(format!("position {}: read failed", position.user_address));
+                continue;
+            };
+            report.positions_checked += 1;
+            let mismatches = compare_position(&position, &chain);
+            if !mismatches.is_empty() {
+                debug!(user = %position.user_address, count = mismatches.len(), "Position differs");
+                report.mismatches.extend(mismatches);
+                report
+                    .repairs
+                    .push(Repair::Position(Box::new(repaired_position(
+                        position, &chain,
+                    ))));
+            }
+        }
+        Ok(())
+    }
+
+    async fn verify_rounds(&self, report: &mut VerifyReport) -> Result<()> {
+        let Some(dead_pool) = self.dead_pool else {
+            report
+                .skipped
+                .push("rounds: DeadPool is not indexed".into());
+            return Ok(());
+        };
+        let rounds = match self.store.get_active_rounds(ACTIVE_ROUND_LIMIT).await {
+            Ok(rounds) => rounds,
+            Err(e) => {
+                report.skipped.push(format!("rounds: {e}"));
+                return Ok(());
+            }
+        };
+
+        let mut readable = Vec::with_capacity(rounds.len());
+        let mut calls = Vec::with_capacity(rounds.len());
+        for round in rounds {
+            match round.round_id.parse::<U256>() {
+                Ok(round_id) => {
+                    calls.push(dead_pool::getRoundCall { roundId: round_id });
+                    readable.push(round);
+                }
+                Err(_) => report
+                    .skipped
+                    .push(format!("round {}: invalid round ID", round.round_id)),
+            }
+        }
+        let on_chain = self.aggregate(dead_pool, &calls, report.block).await?;
+
+        for (round, chain) in readable.into_iter().zip(on_chain) {
+            let Some(chain) = chain else {
+                report
+                    .skipped
+                    .push(format!("round {}: read failed", round.round_id));
+                continue;
+            };
+            report.rounds_checked += 1;
+            let mismatches = compare_round(&round, &chain);
+            if !mismatches.is_empty() {
+                report.mismatches.extend(mismatches);
+                report
+                    .repairs
+                    .push(Repair::Round(Box::new(repaired_round(round, &chain))));
+            }
+        }
+        Ok(())
+    }
+
+    async fn verify_stats(&self, report: &mut VerifyReport) -> Result<()> {
+        let calls: Vec<_> = Level::all_valid()
+            .into_iter()
+            .map(|level| ghost_core::getLevelStateCall {
+                level: u8::from(level),
+            })
+            .collect();
+        let on_chain = self
+            .aggregate(self.ghost_core, &calls, report.block)
+            .await?;
+        for (level, state) in Level::all_valid().into_iter().zip(on_chain) {
+            let Some(state) = state else {
+                report
+                    .skipped
+                    .push(format!("level {}: read failed", level.name()));
+                continue;
+            };
+            let indexed = self.store.get_level_stats(level).await?;
+            report.mismatches.extend(compare_level(&indexed, &state));
+        }
+
+        let tvl = self
+            .aggregate(
+                self.ghost_core,
+                &[ghost_core::getTotalValueLockedCall {}],
+                report.block,
+            )
+            .await?;
+        if let Some(Some(tvl)) = tvl.into_iter().next() {
+            let global = self.store.get_global_stats().await?;
+            let on_chain = data_amount(tvl);
+            if global.total_value_locked != on_chain {
+                report.mismatches.push(Mismatch::new(
+                    Subject::Global,
+                    "total_value_locked",
+                    &global.total_value_locked,
+                    &on_chain,
+                    Severity::Minor,
+                ));
+            }
+        } else {
+            report.skipped.push("global: read failed".into());
+        }
+        Ok(())
+    }
+
+    // ───────────────────────────────────────────────────────────────────────────
+    // Batched reads
+    // ───────────────────────────────────────────────────────────────────────────
+
+    /// Call `calls` on `target` at `block` through `aggregate3`.
+    ///
+    /// Calls that revert or return malformed data come back as `None`.
+    async fn aggregate<C: SolCall + Sync>(
+        &self,
+        target: Address,
+        calls: &[C],
+        block: BlockNumber,
+    ) -> Result<Vec<Option<C::Return>>> {
+        let mut results = Vec::with_capacity(calls.len());
+        for batch in calls.chunks(self.batch_size) {
+            let call = aggregate3Call {
+                calls: batch
+                    .iter()
+                    .map(|call| Call3 {
+                        target,
+                        allowFailure: true,
+                        callData: call.abi_encode().into(),
+                    })
+                    .collect(),
+            };
+            let request = TransactionRequest::default()
+                .to(self.multicall)
+                .input(call.abi_encode().into());
+            let output = self
+                .provider
+                .call(request)
+                .block(block.value().into())
+                .await
+                .map_err(|e| InfraError::Rpc(Box::new(e)))?;
+            let returns = aggregate3Call::abi_decode_returns(&output)
+                .map_err(|e| InfraError::EventDecoding(format!("aggregate3: {e}")))?;
+            if returns.len() != batch.len() {
+                return Err(InfraError::EventDecoding(format!(
+                    "aggregate3 returned {} results, expected {}",
+                    returns.len(),
+                    batch.len()
+                ))
+                .into());
+            }
+            results.extend(returns.into_iter().map(|result| {
+                result
+                    .success
+                    .then(|| C::abi_decode_returns(&result.returnData).ok())
+                    .flatten()
+            }));
+        }
+        Ok(results)
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// COMPARISON
+// ═══════════════════════════════════════════════════════════════════════════════
+
+fn data_amount(wei: U256) -> TokenAmount {
+    TokenAmount::from_wei(wei, DATA_TOKEN_DECIMALS)
+}
+
+/// `size` positions picked at random.
+fn sample_positions(mut positions: Vec<Position>, size: usize) -> Vec<Position> {
+    let state = RandomState::new();
+    positions.sort_by_cached_key(|position| state.hash_one(position.user_address));
+    positions.truncate(size);
+    positions
+}
+
+/// Differences between an active indexed position and its on-chain state.
+///
+/// A position closed on chain is reported as such alone.
+fn compare_position(position: &Position, chain: &ghost_core::Position) -> Vec<Mismatch> {
+    let subject = Subject::Position(position.user_address);
+    if !chain.alive || chain.level == 0 {
+        return vec![Mismatch::new(
+            subject,
+            "is_alive",
+            &true,
+            &false,
+            Severity::Critical,
+        )];
+    }
+
+    let mut mismatches = Vec::new();
+    if u8::from(position.level) != chain.level {
+        let on_chain = Level::try_from(chain.level).map_or_else(
+            |_| chain.level.to_string(),
+            |level| level.name().to_string(),
+        );
+        mismatches.push(Mismatch::new(
+            subject.clone(),
+            "level",
+            &position.level.name(),
+            &on_chain,
+            Severity::Critical,
+        ));
+    }
+    let amount = data_amount(chain.amount);
+    if position.amount != amount {
+        mismatches.push(Mismatch::new(
+            subject.clone(),
+            "amount",
+            &position.amount,
+            &amount,
+            Severity::Critical,
+        ));
+    }
+    if position.ghost_streak.value() != i32::from(chain.ghostStreak) {
+        mismatches.push(Mismatch::new(
+            subject,
+            "ghost_streak",
+            &position.ghost_streak,
+            &chain.ghostStreak,
+            Severity::Major,
+        ));
+    }
+    mismatches
+}
+
+/// `position` with the compared fields taken from the chain.
+fn repaired_position(mut position: Position, chain: &ghost_core::Position) -> Position {
+    let now = Utc::now();
+    if !chain.alive || chain.level == 0 {
+        // A dead position keeps its level; extracted and culled ones are deleted
+        position.is_alive = false;
+        position.exit_timestamp = Some(now);
+        if chain.level != 0 {
+            position.exit_reason = Some(ExitReason::Traced);
+        }
+    } else {
+        position.level = Level::try_from(chain.level).unwrap_or(position.level);
+        // The effective stake is reweighed by the stats recompute that follows
+        position.amount = data_amount(chain.amount);
+        position.ghost_streak = GhostStreak::new_unchecked(i32::from(chain.ghostStreak));
+    }
+    position.updated_at = now;
+    position
+}
+
+/// Differences between an active indexed round and its on-chain state.
+fn compare_round(round: &Round, chain: &dead_pool::Round) -> Vec<Mismatch> {
+    let subject = || Subject::Round(round.round_id.clone());
+    let mut mismatches = Vec::new();
+    if round.is_resolved != chain.resolved {
+        mismatches.push(Mismatch::new(
+            subject(),
+            "is_resolved",
+            &round.is_resolved,
+            &chain.resolved,
+            Severity::Critical,
+        ));
+    }
+    for (field, indexed, on_chain) in [
+        ("over_pool", &round.over_pool, chain.overPool),
+        ("under_pool", &round.under_pool, chain.underPool),
+    ] {
+        let on_chain = data_amount(on_chain);
+        if *indexed != on_chain {
+            mismatches.push(Mismatch::new(
+                subject(),
+                field,
+                indexed,
+                &on_chain,
+                Severity::Major,
+            ));
+        }
+    }
+    mismatches
+}
+
+/// `round` with the compared fields taken from the chain.
+fn repaired_round(mut round: Round, chain: &dead_pool::Round) -> Round {
+    round.over_pool = data_amount(chain.overPool);
+    round.under_pool = data_amount(chain.underPool);
+    round.is_resolved = chain.resolved;
+    round.outcome = chain.resolved.then_some(chain.outcome);
+    round
+}
+
+/// Differences between a level's indexed stats and its on-chain state.
+fn compare_level(stats: &LevelStats, chain: &ghost_core::LevelState) -> Vec<Mismatch> {
+    let subject = || Subject::Level(stats.level);
+    let mut mismatches = Vec::new();
+    let staked = data_amount(chain.totalStaked);
+    if stats.total_staked != staked {
+        mismatches.push(Mismatch::new(
+            subject(),
+            "total_staked",
+            &stats.total_staked,
+            &staked,
+            Severity::Minor,
+        ));
+    }
+    if U256::from(stats.alive_count) != chain.aliveCount {
+        mismatches.push(Mismatch::new(
+            subject(),
+            "alive_count",
+            &stats.alive_count,
+            &chain.aliveCount,
+            Severity::Minor,
+        ));
+    }
+    mismatches
+}
+
+/// The recomputed value of a stats `field`.
+fn recomputed(
+    levels: &[LevelStats],
+    global: &GlobalStats,
+    subject: &Subject,
+    field: &str,
+) -> String {
+    match (subject, field) {
+        (Subject::Global, _) => global.total_value_locked.to_string(),
+        (Subject::Level(level), field) => levels
+            .iter()
+            .find(|stats| stats.level == *level)
+            .map_or_else(String::new, |stats| match field {
+                "alive_count" => stats.alive_count.to_string(),
+                _ => stats.total_staked.to_string(),
+            }),
+        _ => String::new(),
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use alloy::primitives::Bytes;
+    use alloy::providers::ProviderBuilder;
+    use alloy::providers::mock::Asserter;
+    use alloy::sol_types::SolValue;
+    use chrono::TimeZone;
+    use sqlx::postgres::PgPoolOptions;
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::store::PostgresStore;
+
+    fn position(user: u8, level: Level, amount: u64, streak: i32) -> Position {
+        let entry = Utc.timestamp_opt(1_700_000_000, 0).unwrap();
+        Position {
+            id: Uuid::new_v4(),
+            user_address: EthAddress::new([user; 20]),
+            level,
+            amount: data_amount(U256::from(amount)),
+            effective_stake: data_amount(U256::from(amount)),
+            reward_debt: TokenAmount::zero(),
+            entry_timestamp: entry,
+            last_add_timestamp: None,
+            ghost_streak: GhostStreak::new_unchecked(streak),
+            is_alive: true,
+            is_extracted: false,
+            exit_reason: None,
+            exit_timestamp: None,
+            survival_seconds: None,
+            scans_survived: None,
+            extracted_amount: None,
+            extracted_rewards: None,
+            created_at_block: BlockNumber::new(1),
+            updated_at: entry,
+        }
+    }
+
+    fn on_chain(level: Level, amount: u64, streak: u16, alive: bool) -> ghost_core::Position {
+        ghost_core::Position {
+            amount: U256::from(amount),
+            level: u8::from(level),
+            entryTimestamp: 1_700_000_000,
+            lastAddTimestamp: 0,
+            rewardDebt: U256::ZERO,
+            alive,
+            ghostStreak: streak,
+        }
+    }
+
+    #[test]
+    fn matching_position_has_no_mismatches() {
+        let indexed = position(1, Level::Subnet, 100, 3);
+
+        assert!(compare_position(&indexed, &on_chain(Level::Subnet, 100, 3, true)).is_empty());
+    }
+
+    #[test]
+    fn diverging_position_fields_are_graded() {
+        let indexed = position(1, Level::Subnet, 100, 3);
+
+        let mismatches = compare_position(&indexed, &on_chain(Level::Darknet, 250, 4, true));
+
+        let graded: Vec<_> = mismatches.iter().map(|m| (m.field, m.severity)).collect();
+        assert_eq!(
+            graded,
+            [
+                ("level", Severity::Critical),
+                ("amount", Severity::Critical),
+                ("ghost_streak", Severity::Major),
+            ]
+        );
+        assert_eq!(
+            mismatches[1].indexed,
+            data_amount(U256::from(100)).to_string()
+        );
+        assert_eq!(
+            mismatches[1].on_chain,
+            data_amount(U256::from(250)).to_string()
+        );
+    }
+
+    #[test]
+    fn position_closed_on_chain_is_closed_by_the_repair() {
+        let indexed = position(1, Level::Subnet, 100, 3);
+        let dead = on_chain(Level::Subnet, 100, 3, false);
+
+        let mismatches = compare_position(&indexed, &dead);
+        let repaired = repaired_position(indexed.clone(), &dead);
+        let deleted = ghost_core::Position {
+            level: 0,
+            ..on_chain(Level::Subnet, 0, 0, false)
+        };
+        let extracted = repaired_position(indexed, &deleted);
+
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].field, "is_alive");
+        assert!(!repaired.is_alive);
+        assert_eq!(repaired.exit_reason, Some(ExitReason::Traced));
+        assert!(!extracted.is_alive);
+        assert_eq!(extracted.exit_reason, None);
+    }
+
+    #[test]
+    fn repair_takes_the_compared_fields_from_chain() {
+        let indexed = position(1, Level::Subnet, 100, 3);
+
+        let repaired = repaired_position(indexed.clone(), &on_chain(Level::Darknet, 250, 4, true));
+
+        assert_eq!(repaired.id, indexed.id);
+        assert_eq!(repaired.level, Level::Darknet);
+        assert_eq!(repaired.amount, data_amount(U256::from(250)));
+        assert_eq!(repaired.ghost_streak.value(), 4);
+        assert!(repaired.is_alive);
+    }
+
+    #[test]
+    fn sample_picks_distinct_positions() {
+        let positions: Vec<_> = (1..=10).map(|i| position(i, Level::Vault, 1, 0)).collect();
+
+        let sampled = sample_positions(positions, 4);
+
+        let users: HashSet<_> = sampled.iter().map(|p| p.user_address).collect();
+        assert_eq!(users.len(), 4);
+    }
+
+    #[test]
+    fn severities_order_by_importance() {
+        assert!(Severity::Critical > Severity::Major);
+        assert!(Severity::Major > Severity::Minor);
+    }
+
+    #[test]
+    fn journal_entries_serialize_with_their_subject() {
+        let entry = JournalEntry {
+            at: Utc.timestamp_opt(1_700_000_000, 0).unwrap(),
+            block: BlockNumber::new(7),
+            subject: Subject::Level(Level::Vault),
+            field: "alive_count",
+            before: "2".into(),
+            after: "3".into(),
+        };
+
+        let json = serde_json::to_value(&entry).unwrap();
+
+        assert_eq!(json["block"], 7);
+        assert_eq!(json["subject"]["kind"], "level");
+        assert_eq!(json["field"], "alive_count");
+    }
+
+    #[tokio::test]
+    async fn reads_are_batched_and_failed_calls_come_back_empty() {
+        let asserter = Asserter::new();
+        let provider = ProviderBuilder::new().connect_mocked_client(asserter.clone());
+        // Batched reads never touch the database
+        let pool = PgPoolOptions::new()
+            .connect_lazy("postgres://localhost/unused")
+            .unwrap();
+        let verifier = StateVerifier::new(
+            Arc::new(PostgresStore::new(pool)),
+            provider,
+            Address::repeat_byte(1),
+        )
+        .with_batch_size(2);
+        let result = |value: Option<u64>| Result3 {
+            success: value.is_some(),
+            returnData: value.map_or_else(Bytes::new, |v| U256::from(v).abi_encode().into()),
+        };
+        asserter.push_success(&Bytes::from(
+            vec![result(Some(1)), result(None)].abi_encode(),
+        ));
+        asserter.push_success(&Bytes::from(vec![result(Some(3))].abi_encode()));
+
+        let calls = vec![ghost_core::getTotalValueLockedCall {}; 3];
+        let values = verifier
+            .aggregate(Address::repeat_byte(2), &calls, BlockNumber::new(10))
+            .await
+            .unwrap();
+
+        assert_eq!(values, [Some(U256::from(1)), None, Some(U256::from(3))]);
+    }
+}
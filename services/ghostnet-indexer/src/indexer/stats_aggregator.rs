@@ -0,0 +1,1840 @@
+//! Incremental aggregate statistics.
+//!
+//! The [`StatsAggregator`] keeps an in-memory view of [`LevelStats`] and
+//! [`GlobalStats`] that handlers update through the [`StatsSink`] port.
+//! Deltas are merged per level and written to the [`StatsStore`] in batches,
+//! either every `flush_interval` or as soon as `flush_threshold` deltas are
+//! buffered.
+//!
+//! Token flow deltas are merged per hourly bucket and written to the
+//! [`TokenFlowStore`] aggregates in the same flush, as are the per-scan level
+//! snapshots behind the level history series.
+//!
+//! # Data Flow
+//!
+//! ```text
+//! PositionHandler ─┐
+//! DeathHandler ────┤
+//! ScanHandler ─────┼──▶ StatsAggregator ──(batched)──▶ StatsStore (+ scan history)
+//! TokenHandler ────┘          ├──(batched)──▶ TokenFlowStore (hourly buckets)
+//!                             └──(after flush)──▶ Cache (level + global stats)
+//! ```
+//!
+//! # Reconciliation
+//!
+//! Incremental counters can drift if an event is missed or replayed.
+//! [`StatsAggregator::recompute_from_db`] rebuilds the derivable counters from
+//! the raw position and death tables and replaces the in-memory view. It is
+//! exposed through the `recompute-stats` CLI subcommand.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, DurationRound, TimeDelta, Utc};
+use parking_lot::Mutex;
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, error, info, instrument, warn};
+
+use crate::config::StatsSettings;
+use crate::error::Result;
+use crate::ports::{Cache, Clock, StatsSink, StatsStore, SystemClock, TokenFlowStore};
+use crate::types::entities::{
+    GlobalStats, GlobalStatsDelta, LevelScanStats, LevelStats, LevelStatsDelta, TokenFlowDelta,
+};
+use crate::types::enums::Level;
+use crate::types::primitives::{BlockNumber, TokenAmount};
+
+/// Window for the rolling death count.
+const DEATH_WINDOW: TimeDelta = TimeDelta::hours(24);
+
+/// Granularity of the token flow aggregates.
+const FLOW_BUCKET: TimeDelta = TimeDelta::hours(1);
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// STATS AGGREGATOR
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Incrementally maintained level and global statistics.
+///
+/// Cheap to read: [`Self::level_stats`] and [`Self::global_stats`] are served
+/// from memory and include deltas that have not been flushed yet.
+pub struct StatsAggregator<S, C, K = SystemClock> {
+    /// Stats store for persistence.
+    store: Arc<S>,
+    /// Cache for invalidation after flushes.
+    cache: Arc<C>,
+    /// Time source for the rolling death window.
+    clock: Arc<K>,
+    /// Interval between periodic flushes.
+    flush_interval: Duration,
+    /// Buffered delta count that triggers an early flush.
+    flush_threshold: usize,
+    /// In-memory view and pending deltas.
+    state: Mutex<AggregatorState>,
+    /// Serializes flushes so batches are written in order.
+    flush_lock: tokio::sync::Mutex<()>,
+    /// Wakes the flush task once `flush_threshold` is reached.
+    flush_needed: Notify,
+}
+
+/// Mutable aggregator state.
+#[derive(Debug, Default)]
+struct AggregatorState {
+    /// Current level statistics, including unflushed deltas.
+    levels: HashMap<Level, LevelStats>,
+    /// Current event-sourced global counters.
+    global: Option<GlobalStats>,
+    /// Deltas not yet written to the store.
+    pending_levels: HashMap<Level, LevelStatsDelta>,
+    /// Global delta not yet written to the store.
+    pending_global: GlobalStatsDelta,
+    /// Token flow deltas not yet written to the store, keyed by bucket start.
+    pending_flows: BTreeMap<DateTime<Utc>, TokenFlowDelta>,
+    /// Finalized scans not yet written to the store.
+    pending_scans: Vec<LevelScanStats>,
+    /// Number of deltas recorded since the last flush.
+    pending_count: usize,
+    /// Death counts keyed by time, per level, for the rolling window.
+    recent_deaths: HashMap<Level, BTreeMap<DateTime<Utc>, u32>>,
+}
+
+impl<S, C> StatsAggregator<S, C>
+where
+    S: StatsStore + TokenFlowStore,
+    C: Cache,
+{
+    /// Create a new aggregator using the system clock.
+    #[must_use]
+    pub fn new(store: Arc<S>, cache: Arc<C>, settings: &StatsSettings) -> Self {
+        Self::with_clock(store, cache, Arc::new(SystemClock), settings)
+    }
+}
+
+impl<S, C, K> StatsAggregator<S, C, K>
+where
+    S: StatsStore + TokenFlowStore,
+    C: Cache,
+    K: Clock,
+{
+    /// Create a new aggregator with a custom clock.
+    #[must_use]
+    pub fn with_clock(
+        store: Arc<S>,
+        cache: Arc<C>,
+        clock: Arc<K>,
+        settings: &StatsSettings,
+    ) -> Self {
+        Self {
+            store,
+            cache,
+            clock,
+            flush_interval: settings.flush_interval(),
+            flush_threshold: settings.flush_threshold.max(1),
+            state: Mutex::new(AggregatorState::default()),
+            flush_lock: tokio::sync::Mutex::new(()),
+            flush_needed: Notify::new(),
+        }
+    }
+
+    /// Load the persisted statistics into memory.
+    ///
+    /// Call once at startup, before events are processed. Unflushed deltas
+    /// are replayed on top of the loaded counters, but the rolling death
+    /// window is taken from the store as-is.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the stats cannot be read from the store.
+    #[instrument(skip(self))]
+    pub async fn load(&self) -> Result<()> {
+        let levels = self.store.get_all_level_stats().await?;
+        let global = self.store.get_global_stats().await?;
+        let deaths = self.load_death_window().await?;
+
+        self.replace_state(levels, global, deaths);
+        info!("Aggregate stats loaded");
+        Ok(())
+    }
+
+    /// Current statistics for a level.
+    #[must_use]
+    pub fn level_stats(&self, level: Level) -> LevelStats {
+        let window_start = self.window_start();
+        let state = self.state.lock();
+        let mut current = state
+            .levels
+            .get(&level)
+            .cloned()
+            .unwrap_or_else(|| LevelStats::empty(level, self.clock.now()));
+        current.deaths_24h = state.deaths_in_window(level, window_start);
+        drop(state);
+        current
+    }
+
+    /// Current statistics for every level that has seen activity, ordered by level.
+    #[must_use]
+    pub fn all_level_stats(&self) -> Vec<LevelStats> {
+        let window_start = self.window_start();
+        let state = self.state.lock();
+        let mut levels: Vec<_> = state
+            .levels
+            .values()
+            .cloned()
+            .map(|mut stats| {
+                stats.deaths_24h = state.deaths_in_window(stats.level, window_start);
+                stats
+            })
+            .collect();
+        drop(state);
+        levels.sort_by_key(|stats| stats.level as i16);
+        levels
+    }
+
+    /// Current global statistics.
+    ///
+    /// Level-derived totals are summed from the in-memory level view.
+    #[must_use]
+    pub fn global_stats(&self) -> GlobalStats {
+        let state = self.state.lock();
+        let now = self.clock.now();
+        let mut global = state.global.clone().unwrap_or_else(|| empty_global(now));
+
+        global.total_value_locked = TokenAmount::zero();
+        global.total_positions = 0;
+        global.total_deaths = 0;
+        for stats in state.levels.values() {
+            global.total_value_locked = global
+                .total_value_locked
+                .saturating_add(&stats.total_staked);
+            global.total_positions = global.total_positions.saturating_add(stats.alive_count);
+            global.total_deaths = global.total_deaths.saturating_add(stats.total_deaths);
+            global.updated_at = global.updated_at.max(stats.updated_at);
+        }
+        drop(state);
+        global
+    }
+
+    /// Number of deltas recorded since the last flush.
+    #[must_use]
+    pub fn pending(&self) -> usize {
+        self.state.lock().pending_count
+    }
+
+    /// Write buffered deltas to the store and refresh the caches.
+    ///
+    /// Deltas for the same level (or token flow bucket) are merged, so a flush
+    /// issues at most one update per level and bucket. If a write fails, the
+    /// unwritten deltas are re-queued for the next flush.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a store write fails.
+    #[instrument(skip(self))]
+    pub async fn flush(&self) -> Result<()> {
+        let _guard = self.flush_lock.lock().await;
+
+        let (levels, global, flows, scans) = {
+            let mut state = self.state.lock();
+            state.pending_count = 0;
+            (
+                std::mem::take(&mut state.pending_levels),
+                std::mem::take(&mut state.pending_global),
+                std::mem::take(&mut state.pending_flows),
+                std::mem::take(&mut state.pending_scans),
+            )
+        };
+
+        if levels.is_empty() && global.is_empty() && flows.is_empty() && scans.is_empty() {
+            return Ok(());
+        }
+
+        let mut written = Vec::with_capacity(levels.len());
+        let mut unwritten = levels.into_iter();
+        while let Some((level, delta)) = unwritten.next() {
+            if let Err(e) = self.store.update_level_stats(level, delta.clone()).await {
+                self.requeue(
+                    std::iter::once((level, delta)).chain(unwritten),
+                    global,
+                    flows,
+                );
+                self.requeue_scans(scans);
+                return Err(e);
+            }
+            written.push(level);
+        }
+
+        if !global.is_empty()
+            && let Err(e) = self.store.update_global_stats(global.clone()).await
+        {
+            self.requeue(std::iter::empty(), global, flows);
+            self.requeue_scans(scans);
+            return Err(e);
+        }
+
+        let buckets = flows.len();
+        let mut unwritten = flows.into_iter();
+        while let Some((bucket, delta)) = unwritten.next() {
+            if let Err(e) = self.store.record_token_flows(bucket, &delta).await {
+                let flows = std::iter::once((bucket, delta)).chain(unwritten).collect();
+                self.requeue(std::iter::empty(), GlobalStatsDelta::default(), flows);
+                self.requeue_scans(scans);
+                return Err(e);
+            }
+        }
+
+        if let Err(e) = self.store.record_level_scans(&scans).await {
+            self.requeue_scans(scans);
+            return Err(e);
+        }
+
+        for level in &written {
+            self.cache.invalidate_level(level);
+        }
+        let refreshed = self.store.refresh_global_stats().await?;
+        self.cache.set_global_stats(refreshed);
+
+        debug!(
+            levels = written.len(),
+            buckets,
+            scans = scans.len(),
+            "Aggregate stats flushed"
+        );
+        Ok(())
+    }
+
+    /// Rebuild statistics from the raw tables, correcting any drift.
+    ///
+    /// Pending deltas are flushed first. Returns the recomputed level stats.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the flush or the recomputation fails.
+    #[instrument(skip(self))]
+    pub async fn recompute_from_db(&self) -> Result<Vec<LevelStats>> {
+        self.flush().await?;
+
+        let _guard = self.flush_lock.lock().await;
+        let recomputed = self.store.recompute_level_stats().await?;
+        let global = self.store.refresh_global_stats().await?;
+        let deaths = self.load_death_window().await?;
+
+        {
+            let state = self.state.lock();
+            for stats in &recomputed {
+                if state
+                    .levels
+                    .get(&stats.level)
+                    .is_some_and(|s| !same_counters(s, stats))
+                {
+                    warn!(level = ?stats.level, "Level stats drift corrected");
+                }
+            }
+        }
+
+        for stats in &recomputed {
+            self.cache.invalidate_level(&stats.level);
+        }
+        self.cache.set_global_stats(global.clone());
+        self.replace_state(recomputed.clone(), global, deaths);
+
+        info!(
+            levels = recomputed.len(),
+            "Aggregate stats recomputed from database"
+        );
+        Ok(recomputed)
+    }
+
+    /// Spawn a task that flushes every `flush_interval`, or earlier once
+    /// `flush_threshold` deltas are buffered.
+    ///
+    /// When `shutdown` is cancelled the task performs a final flush before
+    /// exiting.
+    pub fn spawn_flush_task(self: &Arc<Self>, shutdown: CancellationToken) -> JoinHandle<()>
+    where
+        S: 'static,
+        C: 'static,
+        K: 'static,
+    {
+        let aggregator = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(aggregator.flush_interval);
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+            loop {
+                tokio::select! {
+                    () = shutdown.cancelled() => break,
+                    _ = ticker.tick() => {}
+                    () = aggregator.flush_needed.notified() => {}
+                }
+                if let Err(e) = aggregator.flush().await {
+                    warn!(error = %e, "Stats flush failed, deltas re-queued");
+                }
+            }
+
+            if let Err(e) = aggregator.flush().await {
+                error!(error = %e, "Failed to flush stats on shutdown");
+            }
+        })
+    }
+
+    /// Start of the rolling death window.
+    fn window_start(&self) -> DateTime<Utc> {
+        self.clock.now() - DEATH_WINDOW
+    }
+
+    /// Read the rolling death window from the store.
+    async fn load_death_window(&self) -> Result<Vec<(Level, DateTime<Utc>, u32)>> {
+        self.store
+            .get_recent_death_counts(self.window_start())
+            .await
+    }
+
+    /// Replace the in-memory view, replaying any pending deltas on top.
+    fn replace_state(
+        &self,
+        levels: Vec<LevelStats>,
+        global: GlobalStats,
+        deaths: Vec<(Level, DateTime<Utc>, u32)>,
+    ) {
+        let mut state = self.state.lock();
+        let now = self.clock.now();
+
+        state.levels = levels.into_iter().map(|s| (s.level, s)).collect();
+        let pending: Vec<_> = state
+            .pending_levels
+            .iter()
+            .map(|(level, delta)| (*level, delta.clone()))
+            .collect();
+        for (level, delta) in pending {
+            state
+                .levels
+                .entry(level)
+                .or_insert_with(|| LevelStats::empty(level, now))
+                .apply(&delta, now);
+        }
+
+        let mut global = global;
+        state.pending_global.apply_to(&mut global);
+        state.global = Some(global);
+
+        let mut recent: HashMap<Level, BTreeMap<DateTime<Utc>, u32>> = HashMap::new();
+        for (level, at, count) in deaths {
+            *recent.entry(level).or_default().entry(at).or_default() += count;
+        }
+        state.recent_deaths = recent;
+    }
+
+    /// Merge deltas that failed to flush back into the pending set.
+    fn requeue(
+        &self,
+        levels: impl IntoIterator<Item = (Level, LevelStatsDelta)>,
+        global: GlobalStatsDelta,
+        flows: BTreeMap<DateTime<Utc>, TokenFlowDelta>,
+    ) {
+        let mut state = self.state.lock();
+        for (level, delta) in levels {
+            state.pending_levels.entry(level).or_default().merge(delta);
+            state.pending_count += 1;
+        }
+        if !global.is_empty() {
+            state.pending_global.merge(global);
+            state.pending_count += 1;
+        }
+        for (bucket, delta) in flows {
+            state.pending_flows.entry(bucket).or_default().merge(delta);
+            state.pending_count += 1;
+        }
+    }
+
+    /// Put scans that failed to flush back in front of the pending ones.
+    fn requeue_scans(&self, mut scans: Vec<LevelScanStats>) {
+        if scans.is_empty() {
+            return;
+        }
+        let mut state = self.state.lock();
+        state.pending_count += scans.len();
+        scans.append(&mut state.pending_scans);
+        state.pending_scans = scans;
+    }
+
+    /// Count a buffered delta, returning whether a flush should be triggered.
+    const fn bump_pending(&self, state: &mut AggregatorState) -> bool {
+        state.pending_count += 1;
+        state.pending_count >= self.flush_threshold
+    }
+}
+
+impl<S, C, K> StatsSink for StatsAggregator<S, C, K>
+where
+    S: StatsStore + TokenFlowStore,
+    C: Cache,
+    K: Clock,
+{
+    fn record_level(&self, level: Level, delta: LevelStatsDelta, at: DateTime<Utc>) {
+        if delta.is_empty() {
+            return;
+        }
+
+        let window_start = self.window_start();
+        let mut state = self.state.lock();
+
+        if let Some(deaths) = delta.deaths_delta.filter(|_| at > window_start) {
+            let window = state.recent_deaths.entry(level).or_default();
+            *window.entry(at).or_default() += deaths;
+            // Drop entries that have aged out of the window
+            *window = window.split_off(&window_start);
+        }
+
+        state
+            .levels
+            .entry(level)
+            .or_insert_with(|| LevelStats::empty(level, at))
+            .apply(&delta, at);
+        state.pending_levels.entry(level).or_default().merge(delta);
+        let flush = self.bump_pending(&mut state);
+        drop(state);
+
+        if flush {
+            self.flush_needed.notify_one();
+        }
+    }
+
+    fn record_global(&self, delta: GlobalStatsDelta) {
+        if delta.is_empty() {
+            return;
+        }
+
+        let now = self.clock.now();
+        let mut state = self.state.lock();
+
+        let global = state.global.get_or_insert_with(|| empty_global(now));
+        delta.apply_to(global);
+        global.updated_at = now;
+        state.pending_global.merge(delta);
+        let flush = self.bump_pending(&mut state);
+        drop(state);
+
+        if flush {
+            self.flush_needed.notify_one();
+        }
+    }
+
+    fn record_token_flow(&self, delta: TokenFlowDelta, at: DateTime<Utc>) {
+        if delta.is_empty() {
+            return;
+        }
+
+        let bucket = at.duration_trunc(FLOW_BUCKET).unwrap_or(at);
+        let mut state = self.state.lock();
+        state.pending_flows.entry(bucket).or_default().merge(delta);
+        let flush = self.bump_pending(&mut state);
+        drop(state);
+
+        if flush {
+            self.flush_needed.notify_one();
+        }
+    }
+
+    fn record_scan(
+        &self,
+        level: Level,
+        scan_id: &str,
+        deaths: u32,
+        block: BlockNumber,
+        at: DateTime<Utc>,
+    ) {
+        let mut state = self.state.lock();
+        let (survivors, total_staked) = state.levels.get(&level).map_or_else(
+            || (0, TokenAmount::zero()),
+            |stats| (stats.alive_count, stats.total_staked.clone()),
+        );
+        state.pending_scans.push(LevelScanStats {
+            scan_id: scan_id.to_string(),
+            level,
+            scanned_at: at,
+            block_number: Some(block),
+            deaths,
+            survivors,
+            total_staked,
+        });
+        let flush = self.bump_pending(&mut state);
+        drop(state);
+
+        if flush {
+            self.flush_needed.notify_one();
+        }
+    }
+}
+
+impl<S, C, K> std::fmt::Debug for StatsAggregator<S, C, K> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StatsAggregator")
+            .field("flush_interval", &self.flush_interval)
+            .field("flush_threshold", &self.flush_threshold)
+            .finish_non_exhaustive()
+    }
+}
+
+impl AggregatorState {
+    /// Deaths at `level` recorded after `window_start`.
+    fn deaths_in_window(&self, level: Level, window_start: DateTime<Utc>) -> u32 {
+        self.recent_deaths.get(&level).map_or(0, |window| {
+            window
+                .range(window_start..)
+                .filter(|(at, _)| **at > window_start)
+                .map(|(_, count)| *count)
+                .sum()
+        })
+    }
+}
+
+/// Global stats with every counter at zero.
+fn empty_global(updated_at: DateTime<Utc>) -> GlobalStats {
+    GlobalStats {
+        total_value_locked: TokenAmount::zero(),
+        total_positions: 0,
+        total_deaths: 0,
+        total_burned: TokenAmount::zero(),
+        total_emissions_distributed: TokenAmount::zero(),
+        total_toll_collected: TokenAmount::zero(),
+        total_buyback_burned: TokenAmount::zero(),
+        system_reset_count: 0,
+        updated_at,
+    }
+}
+
+/// Compare the counters of two level stats, ignoring timestamps and windows.
+fn same_counters(a: &LevelStats, b: &LevelStats) -> bool {
+    a.total_staked == b.total_staked
+        && a.total_effective_staked == b.total_effective_staked
+        && a.alive_count == b.alive_count
+        && a.total_deaths == b.total_deaths
+        && a.total_extracted == b.total_extracted
+        && a.total_culled == b.total_culled
+        && a.total_burned == b.total_burned
+        && a.total_distributed == b.total_distributed
+        && a.highest_ghost_streak == b.highest_ghost_streak
+        && a.total_ghost_streak == b.total_ghost_streak
+        && a.total_survival_seconds == b.total_survival_seconds
+        && a.survival_samples == b.survival_samples
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::significant_drop_tightening)]
+mod tests {
+    use std::sync::Mutex as StdMutex;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+    use alloy::primitives::{Address, U256};
+    use async_trait::async_trait;
+    use chrono::TimeZone;
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::abi::{data_token, ghost_core, trace_scan};
+    use crate::config::TokenFlowSettings;
+    use crate::error::InfraError;
+    use crate::handlers::{
+        DeathHandler, DeathPort, PositionHandler, PositionPort, ScanHandler, ScanPort,
+        TokenHandler, TokenPort,
+    };
+    use crate::ports::{BoostStore, DeathStore, FakeClock, MockCache, PositionStore, ScanStore};
+    use crate::types::entities::{
+        AddressFlows, Boost, BurnRate, CascadeEarnings, CascadeShare, Death, EffectiveStakeChange,
+        ExitStreakCount, HistoryBucket, HistoryCursor, LapsedBoost, LevelHistoryPoint,
+        LevelSurvival, OutboxEvent, Page, Position, PositionFilter, PositionHistoryEntry, Scan,
+        ScanFinalizationData, TokenTransfer,
+    };
+    use crate::types::enums::{BoostType, ExitReason};
+    use crate::types::events::EventMetadata;
+    use crate::types::primitives::{EthAddress, GhostStreak};
+
+    // ═══════════════════════════════════════════════════════════════════════════
+    // MOCK STORE
+    // ═══════════════════════════════════════════════════════════════════════════
+
+    /// In-memory store holding raw records and the materialized stats.
+    ///
+    /// `recompute_level_stats` mirrors the SQL in `PostgresStore`.
+    #[derive(Debug)]
+    struct MemoryStore {
+        clock: Arc<FakeClock>,
+        positions: StdMutex<Vec<Position>>,
+        boosts: StdMutex<Vec<Boost>>,
+        scans: StdMutex<Vec<Scan>>,
+        deaths: StdMutex<Vec<Death>>,
+        levels: StdMutex<HashMap<Level, LevelStats>>,
+        global: StdMutex<GlobalStats>,
+        flows: StdMutex<BTreeMap<DateTime<Utc>, TokenFlowDelta>>,
+        transfers: StdMutex<Vec<TokenTransfer>>,
+        scan_history: StdMutex<Vec<LevelScanStats>>,
+        level_updates: AtomicUsize,
+        fail_updates: AtomicBool,
+    }
+
+    impl MemoryStore {
+        fn new(clock: Arc<FakeClock>) -> Self {
+            let now = clock.now();
+            Self {
+                levels: StdMutex::new(
+                    (0..=5)
+                        .filter_map(|l| Level::try_from(l).ok())
+                        .map(|l| (l, LevelStats::empty(l, now)))
+                        .collect(),
+                ),
+                global: StdMutex::new(empty_global(now)),
+                clock,
+                positions: StdMutex::default(),
+                boosts: StdMutex::default(),
+                scans: StdMutex::default(),
+                deaths: StdMutex::default(),
+                flows: StdMutex::default(),
+                transfers: StdMutex::default(),
+                scan_history: StdMutex::default(),
+                level_updates: AtomicUsize::new(0),
+                fail_updates: AtomicBool::new(false),
+            }
+        }
+
+        /// Weigh the active positions among `ids`, or all of them, by their
+        /// boosts active at `now`, returning those that changed.
+        fn reweigh(&self, ids: Option<&[Uuid]>, now: DateTime<Utc>) -> Vec<EffectiveStakeChange> {
+            let mut positions = self.positions.lock().unwrap();
+            let boosts = self.boosts.lock().unwrap();
+            let mut changes = Vec::new();
+            for position in positions.iter_mut().filter(|p| p.is_active()) {
+                if ids.is_some_and(|ids| !ids.contains(&position.id)) {
+                    continue;
+                }
+                let active: Vec<_> = boosts
+                    .iter()
+                    .filter(|b| b.position_id == position.id && b.is_active(now))
+                    .cloned()
+                    .collect();
+                let previous = position.effective_stake.clone();
+                position.apply_boosts(&active);
+                if position.effective_stake != previous {
+                    changes.push(EffectiveStakeChange {
+                        position_id: position.id,
+                        level: position.level,
+                        previous,
+                        current: position.effective_stake.clone(),
+                    });
+                }
+            }
+            changes
+        }
+
+        fn persisted(&self, level: Level) -> LevelStats {
+            self.levels.lock().unwrap()[&level].clone()
+        }
+
+        #[allow(clippy::cast_possible_truncation)]
+        fn deaths_24h(&self, level: Level) -> u32 {
+            let since = self.clock.now() - DEATH_WINDOW;
+            let records = self
+                .deaths
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|d| d.level == level && d.scan_id.is_none() && d.created_at > since)
+                .count() as u32;
+            let scanned: u32 = self
+                .scans
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|s| s.level == level && s.finalized_at.is_some_and(|at| at > since))
+                .filter_map(|s| s.death_count)
+                .sum();
+            records + scanned
+        }
+
+        /// Flow buckets overlapping the trailing `window`, merged.
+        fn flows_in_window(&self, window: Duration) -> TokenFlowDelta {
+            let since = self.clock.now() - TimeDelta::from_std(window).unwrap();
+            let since = since.duration_trunc(FLOW_BUCKET).unwrap();
+            let mut total = TokenFlowDelta::default();
+            for delta in self.flows.lock().unwrap().range(since..).map(|(_, d)| d) {
+                total.merge(delta.clone());
+            }
+            total
+        }
+    }
+
+    #[async_trait]
+    impl PositionStore for MemoryStore {
+        async fn get_active_position(&self, address: &EthAddress) -> Result<Option<Position>> {
+            let positions = self.positions.lock().unwrap();
+            Ok(positions
+                .iter()
+                .find(|p| p.user_address == *address && p.is_active())
+                .cloned())
+        }
+
+        async fn save_position(&self, position: &Position) -> Result<()> {
+            let mut positions = self.positions.lock().unwrap();
+            match positions.iter_mut().find(|p| p.id == position.id) {
+                Some(existing) => *existing = position.clone(),
+                None => positions.push(position.clone()),
+            }
+            Ok(())
+        }
+
+        async fn get_at_risk_positions(&self, _: Level, _: u32) -> Result<Vec<Position>> {
+            Ok(vec![])
+        }
+
+        async fn append_history(&self, _: &PositionHistoryEntry) -> Result<()> {
+            Ok(())
+        }
+
+        async fn save_position_with_history(
+            &self,
+            position: &Position,
+            _: &PositionHistoryEntry,
+            _: &[OutboxEvent],
+        ) -> Result<()> {
+            self.save_position(position).await
+        }
+
+        async fn get_history(
+            &self,
+            _: &EthAddress,
+            _: u32,
+            _: Option<HistoryCursor>,
+        ) -> Result<Vec<PositionHistoryEntry>> {
+            Ok(vec![])
+        }
+
+        async fn get_position_by_id(&self, id: &Uuid) -> Result<Option<Position>> {
+            let positions = self.positions.lock().unwrap();
+            Ok(positions.iter().find(|p| p.id == *id).cloned())
+        }
+
+        async fn get_positions_by_level(&self, level: Level) -> Result<Vec<Position>> {
+            let positions = self.positions.lock().unwrap();
+            Ok(positions
+                .iter()
+                .filter(|p| p.level == level && p.is_active())
+                .cloned()
+                .collect())
+        }
+
+        async fn query(&self, _: &PositionFilter, _: u32) -> Result<Page<Position>> {
+            Ok(Page {
+                items: vec![],
+                next_cursor: None,
+            })
+        }
+
+        #[allow(clippy::cast_possible_truncation)]
+        async fn count_positions_by_level(&self, level: Level) -> Result<u32> {
+            Ok(self.get_positions_by_level(level).await?.len() as u32)
+        }
+
+        #[allow(clippy::cast_possible_truncation)]
+        async fn count_scans_survived(
+            &self,
+            level: Level,
+            entered: BlockNumber,
+            exited: BlockNumber,
+        ) -> Result<u32> {
+            let scans = self.scans.lock().unwrap();
+            Ok(scans
+                .iter()
+                .filter(|s| s.level == level)
+                .filter_map(|s| s.finalized_block)
+                .filter(|block| *block > entered && *block < exited)
+                .count() as u32)
+        }
+    }
+
+    #[async_trait]
+    impl ScanStore for MemoryStore {
+        async fn save_scan(&self, scan: &Scan) -> Result<()> {
+            self.scans.lock().unwrap().push(scan.clone());
+            Ok(())
+        }
+
+        async fn finalize_scan(&self, scan_id: &str, data: ScanFinalizationData) -> Result<()> {
+            let mut scans = self.scans.lock().unwrap();
+            let scan = scans
+                .iter_mut()
+                .find(|s| s.scan_id == scan_id)
+                .ok_or(InfraError::NotFound)?;
+            scan.finalized_at = Some(data.finalized_at);
+            scan.death_count = Some(data.death_count);
+            scan.total_dead = Some(data.total_dead);
+            Ok(())
+        }
+
+        async fn get_recent_scans(&self, _: Level, _: u32) -> Result<Vec<Scan>> {
+            Ok(vec![])
+        }
+
+        async fn get_scan_by_id(&self, scan_id: &str) -> Result<Option<Scan>> {
+            let scans = self.scans.lock().unwrap();
+            Ok(scans.iter().find(|s| s.scan_id == scan_id).cloned())
+        }
+
+        async fn get_pending_scans(&self) -> Result<Vec<Scan>> {
+            Ok(vec![])
+        }
+
+        async fn link_deaths_to_scan(&self, scan_id: &str, death_ids: &[Uuid]) -> Result<u64> {
+            let scan = self
+                .get_scan_by_id(scan_id)
+                .await?
+                .ok_or(InfraError::NotFound)?;
+            let mut linked = 0;
+            for death in self.deaths.lock().unwrap().iter_mut() {
+                if death_ids.contains(&death.id) {
+                    death.scan_id = Some(scan.id);
+                    linked += 1;
+                }
+            }
+            Ok(linked)
+        }
+    }
+
+    #[async_trait]
+    impl DeathStore for MemoryStore {
+        async fn record_deaths(&self, deaths: &[Death]) -> Result<()> {
+            self.deaths.lock().unwrap().extend_from_slice(deaths);
+            Ok(())
+        }
+
+        async fn get_deaths_for_scan(&self, _: &str) -> Result<Vec<Death>> {
+            Ok(vec![])
+        }
+
+        async fn get_user_deaths(&self, _: &EthAddress, _: u32) -> Result<Vec<Death>> {
+            Ok(vec![])
+        }
+
+        async fn count_deaths_by_level(&self, level: Level) -> Result<u64> {
+            let deaths = self.deaths.lock().unwrap();
+            Ok(deaths.iter().filter(|d| d.level == level).count() as u64)
+        }
+
+        async fn get_recent_deaths(&self, _: u32) -> Result<Vec<Death>> {
+            Ok(vec![])
+        }
+
+        async fn record_cascade_shares(&self, _: &[CascadeShare]) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_cascade_earnings(&self, _: &EthAddress, _: u32) -> Result<CascadeEarnings> {
+            Err(InfraError::NotFound.into())
+        }
+    }
+
+    #[async_trait]
+    impl StatsStore for MemoryStore {
+        async fn get_global_stats(&self) -> Result<GlobalStats> {
+            Ok(self.global.lock().unwrap().clone())
+        }
+
+        async fn get_level_stats(&self, level: Level) -> Result<LevelStats> {
+            let mut stats = self.persisted(level);
+            stats.deaths_24h = self.deaths_24h(level);
+            Ok(stats)
+        }
+
+        async fn update_level_stats(&self, level: Level, delta: LevelStatsDelta) -> Result<()> {
+            if self.fail_updates.load(Ordering::SeqCst) {
+                return Err(InfraError::Internal("injected failure".into()).into());
+            }
+            self.level_updates.fetch_add(1, Ordering::SeqCst);
+            let now = self.clock.now();
+            let mut levels = self.levels.lock().unwrap();
+            levels
+                .entry(level)
+                .or_insert_with(|| LevelStats::empty(level, now))
+                .apply(&delta, now);
+            Ok(())
+        }
+
+        async fn get_all_level_stats(&self) -> Result<Vec<LevelStats>> {
+            let mut all = Vec::new();
+            for level in (0..=5).filter_map(|l| Level::try_from(l).ok()) {
+                all.push(self.get_level_stats(level).await?);
+            }
+            Ok(all)
+        }
+
+        async fn refresh_global_stats(&self) -> Result<GlobalStats> {
+            let levels = self.levels.lock().unwrap();
+            let mut global = self.global.lock().unwrap();
+            global.total_value_locked = levels.values().fold(TokenAmount::zero(), |acc, s| {
+                acc.saturating_add(&s.total_staked)
+            });
+            global.total_positions = levels.values().map(|s| s.alive_count).sum();
+            global.total_deaths = levels.values().map(|s| s.total_deaths).sum();
+            Ok(global.clone())
+        }
+
+        async fn update_global_stats(&self, delta: GlobalStatsDelta) -> Result<()> {
+            if self.fail_updates.load(Ordering::SeqCst) {
+                return Err(InfraError::Internal("injected failure".into()).into());
+            }
+            delta.apply_to(&mut self.global.lock().unwrap());
+            Ok(())
+        }
+
+        #[allow(clippy::cast_possible_truncation)]
+        async fn recompute_level_stats(&self) -> Result<Vec<LevelStats>> {
+            self.reweigh(None, self.clock.now());
+            {
+                let positions = self.positions.lock().unwrap();
+                let deaths = self.deaths.lock().unwrap();
+                let scans = self.scans.lock().unwrap();
+                let mut levels = self.levels.lock().unwrap();
+
+                for stats in levels.values_mut() {
+                    let level = stats.level;
+                    let at_level: Vec<_> = positions.iter().filter(|p| p.level == level).collect();
+                    let active: Vec<_> = at_level.iter().filter(|p| p.is_active()).collect();
+
+                    stats.total_staked = active
+                        .iter()
+                        .fold(TokenAmount::zero(), |acc, p| acc.saturating_add(&p.amount));
+                    stats.total_effective_staked =
+                        active.iter().fold(TokenAmount::zero(), |acc, p| {
+                            acc.saturating_add(&p.effective_stake)
+                        });
+                    stats.alive_count = active.len() as u32;
+                    stats.total_extracted =
+                        at_level.iter().filter(|p| p.is_extracted).count() as u32;
+                    stats.total_culled = at_level
+                        .iter()
+                        .filter(|p| p.exit_reason == Some(ExitReason::Culled))
+                        .count() as u32;
+                    let survival: Vec<_> =
+                        at_level.iter().filter_map(|p| p.survival_seconds).collect();
+                    stats.total_survival_seconds = survival.iter().sum::<i64>().unsigned_abs();
+                    stats.survival_samples = survival.len() as u32;
+                    stats.total_ghost_streak = active
+                        .iter()
+                        .map(|p| u64::from(p.ghost_streak.value().unsigned_abs()))
+                        .sum();
+                    stats.total_deaths = deaths
+                        .iter()
+                        .filter(|d| d.level == level && d.scan_id.is_none())
+                        .count() as u32
+                        + scans
+                            .iter()
+                            .filter(|s| s.level == level && s.finalized_at.is_some())
+                            .filter_map(|s| s.death_count)
+                            .sum::<u32>();
+                    stats.highest_ghost_streak = at_level
+                        .iter()
+                        .map(|p| p.ghost_streak)
+                        .chain(
+                            deaths
+                                .iter()
+                                .filter(|d| d.level == level)
+                                .filter_map(|d| d.ghost_streak_at_death),
+                        )
+                        .max()
+                        .unwrap_or(GhostStreak::ZERO);
+                }
+            }
+            self.get_all_level_stats().await
+        }
+
+        async fn get_recent_death_counts(
+            &self,
+            since: DateTime<Utc>,
+        ) -> Result<Vec<(Level, DateTime<Utc>, u32)>> {
+            let deaths = self.deaths.lock().unwrap();
+            let scans = self.scans.lock().unwrap();
+            let records = deaths
+                .iter()
+                .filter(|d| d.scan_id.is_none() && d.created_at > since)
+                .map(|d| (d.level, d.created_at, 1));
+            let scanned = scans.iter().filter_map(|s| {
+                let at = s.finalized_at.filter(|at| *at > since)?;
+                Some((s.level, at, s.death_count.unwrap_or(0)))
+            });
+            Ok(records.chain(scanned).collect())
+        }
+
+        async fn get_survival_stats(&self) -> Result<Vec<LevelSurvival>> {
+            Ok(vec![])
+        }
+
+        async fn get_exit_streak_distribution(&self) -> Result<Vec<ExitStreakCount>> {
+            Ok(vec![])
+        }
+
+        async fn record_level_scans(&self, scans: &[LevelScanStats]) -> Result<()> {
+            if self.fail_updates.load(Ordering::SeqCst) {
+                return Err(InfraError::Internal("injected failure".into()).into());
+            }
+            let mut history = self.scan_history.lock().unwrap();
+            for scan in scans {
+                if !history.iter().any(|s| s.scan_id == scan.scan_id) {
+                    history.push(scan.clone());
+                }
+            }
+            drop(history);
+            Ok(())
+        }
+
+        async fn get_level_history(
+            &self,
+            level: Level,
+            from: DateTime<Utc>,
+            to: DateTime<Utc>,
+            bucket: HistoryBucket,
+        ) -> Result<Vec<LevelHistoryPoint>> {
+            let scans: Vec<_> = self
+                .scan_history
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|scan| scan.level == level)
+                .cloned()
+                .collect();
+            Ok(LevelHistoryPoint::series(&scans, from, to, bucket))
+        }
+    }
+
+    #[async_trait]
+    impl TokenFlowStore for MemoryStore {
+        async fn record_transfer(&self, transfer: &TokenTransfer) -> Result<()> {
+            self.transfers.lock().unwrap().push(transfer.clone());
+            Ok(())
+        }
+
+        async fn record_token_flows(
+            &self,
+            bucket: DateTime<Utc>,
+            delta: &TokenFlowDelta,
+        ) -> Result<()> {
+            if self.fail_updates.load(Ordering::SeqCst) {
+                return Err(InfraError::Internal("injected failure".into()).into());
+            }
+            let mut flows = self.flows.lock().unwrap();
+            flows.entry(bucket).or_default().merge(delta.clone());
+            Ok(())
+        }
+
+        async fn get_burn_rate(&self, window: Duration) -> Result<BurnRate> {
+            let total = self.flows_in_window(window);
+            Ok(BurnRate::new(
+                window,
+                total.burned.unwrap_or_default(),
+                total.tax_burned.unwrap_or_default(),
+                total.tax_collected.unwrap_or_default(),
+            ))
+        }
+
+        async fn get_address_flows(
+            &self,
+            address: &EthAddress,
+            window: Duration,
+        ) -> Result<AddressFlows> {
+            let flow = self
+                .flows_in_window(window)
+                .addresses
+                .remove(address)
+                .unwrap_or_default();
+            Ok(AddressFlows {
+                address: *address,
+                window_secs: window.as_secs(),
+                inflow: flow.inflow,
+                outflow: flow.outflow,
+                transfers_in: u64::from(flow.transfers_in),
+                transfers_out: u64::from(flow.transfers_out),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl BoostStore for MemoryStore {
+        async fn save_boost(&self, boost: &Boost) -> Result<()> {
+            self.boosts.lock().unwrap().push(boost.clone());
+            Ok(())
+        }
+
+        async fn get_active_boosts(
+            &self,
+            address: &EthAddress,
+            now: DateTime<Utc>,
+        ) -> Result<Vec<Boost>> {
+            let boosts = self.boosts.lock().unwrap();
+            Ok(boosts
+                .iter()
+                .filter(|b| b.user_address == *address && b.is_active(now))
+                .cloned()
+                .collect())
+        }
+
+        async fn get_expiring_boosts(
+            &self,
+            _: DateTime<Utc>,
+            _: DateTime<Utc>,
+            _: u32,
+        ) -> Result<Vec<Boost>> {
+            Ok(vec![])
+        }
+
+        async fn get_lapsed_boosts(&self, _: DateTime<Utc>, _: u32) -> Result<Vec<LapsedBoost>> {
+            Ok(vec![])
+        }
+
+        async fn expire_boosts(&self, _: &[(Uuid, DateTime<Utc>)]) -> Result<u64> {
+            Ok(0)
+        }
+
+        async fn reweigh_positions(
+            &self,
+            positions: &[Uuid],
+            now: DateTime<Utc>,
+        ) -> Result<Vec<EffectiveStakeChange>> {
+            Ok(self.reweigh(Some(positions), now))
+        }
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════════
+    // HELPERS
+    // ═══════════════════════════════════════════════════════════════════════════
+
+    type Aggregator = StatsAggregator<MemoryStore, MockCache, FakeClock>;
+
+    fn start_time() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 1, 15, 12, 0, 0).unwrap()
+    }
+
+    fn setup(
+        threshold: usize,
+    ) -> (
+        Arc<Aggregator>,
+        Arc<MemoryStore>,
+        Arc<MockCache>,
+        Arc<FakeClock>,
+    ) {
+        let clock = Arc::new(FakeClock::new(start_time()));
+        let store = Arc::new(MemoryStore::new(Arc::clone(&clock)));
+        let cache = Arc::new(MockCache::new());
+        let settings = StatsSettings {
+            flush_interval_ms: 3_600_000,
+            flush_threshold: threshold,
+        };
+        let aggregator = Arc::new(StatsAggregator::with_clock(
+            Arc::clone(&store),
+            Arc::clone(&cache),
+            Arc::clone(&clock),
+            &settings,
+        ));
+        (aggregator, store, cache, clock)
+    }
+
+    fn tokens(n: u64) -> U256 {
+        U256::from(n) * U256::from(10_u64).pow(U256::from(18_u64))
+    }
+
+    fn user(n: u8) -> Address {
+        Address::from([n; 20])
+    }
+
+    fn meta(clock: &FakeClock) -> EventMetadata {
+        EventMetadata {
+            block_number: 1000,
+            block_hash: [1u8; 32].into(),
+            tx_hash: [2u8; 32].into(),
+            tx_index: 0,
+            log_index: 0,
+            timestamp: clock.now(),
+            contract: user(0xff),
+            chain_id: 6343,
+            tx_function: None,
+            tx_from: None,
+        }
+    }
+
+    fn seeded_position(level: Level, amount: u64, streak: i32) -> Position {
+        Position {
+            id: Uuid::new_v4(),
+            user_address: EthAddress::new([0xaa; 20]),
+            level,
+            amount: TokenAmount::from_wei(tokens(amount), 18),
+            effective_stake: TokenAmount::from_wei(tokens(amount), 18),
+            reward_debt: TokenAmount::zero(),
+            entry_timestamp: start_time(),
+            last_add_timestamp: None,
+            ghost_streak: GhostStreak::new(streak).unwrap(),
+            is_alive: true,
+            is_extracted: false,
+            exit_reason: None,
+            exit_timestamp: None,
+            survival_seconds: None,
+            scans_survived: None,
+            extracted_amount: None,
+            extracted_rewards: None,
+            created_at_block: BlockNumber::new(1),
+            updated_at: start_time(),
+        }
+    }
+
+    /// Counters that both the incremental and recomputed paths must agree on.
+    fn counters(
+        stats: &LevelStats,
+    ) -> (String, String, u32, u32, u32, u32, String, String, i32, u64) {
+        (
+            stats.total_staked.to_string(),
+            stats.total_effective_staked.to_string(),
+            stats.alive_count,
+            stats.total_deaths,
+            stats.deaths_24h,
+            stats.total_extracted,
+            stats.total_burned.to_string(),
+            stats.total_distributed.to_string(),
+            stats.highest_ghost_streak.value(),
+            stats.total_ghost_streak,
+        )
+    }
+
+    fn staked_delta(amount: u64) -> LevelStatsDelta {
+        LevelStatsDelta {
+            staked_delta: Some(TokenAmount::from_wei(tokens(amount), 18)),
+            alive_delta: Some(1),
+            ..LevelStatsDelta::default()
+        }
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════════
+    // TESTS
+    // ═══════════════════════════════════════════════════════════════════════════
+
+    #[tokio::test]
+    #[allow(clippy::too_many_lines)] // Drives every handler through a full session
+    async fn delta_accumulation_matches_recomputation() {
+        let (aggregator, store, cache, clock) = setup(usize::MAX);
+        let sink: Arc<dyn StatsSink> = aggregator.clone();
+        let positions = PositionHandler::new(Arc::clone(&store), Arc::clone(&cache))
+            .with_stats(Arc::clone(&sink))
+            .with_boosts(store.clone());
+        let deaths = DeathHandler::new(Arc::clone(&store), Arc::clone(&store), Arc::clone(&cache))
+            .with_stats(Arc::clone(&sink));
+        let scans =
+            ScanHandler::new(Arc::clone(&store), Arc::clone(&cache)).with_stats(Arc::clone(&sink));
+        let token = TokenHandler::new(Arc::clone(&cache)).with_stats(Arc::clone(&sink));
+
+        // Pre-existing position with a streak, picked up by the baseline recompute
+        store
+            .save_position(&seeded_position(Level::Subnet, 400, 7))
+            .await
+            .unwrap();
+        aggregator.recompute_from_db().await.unwrap();
+
+        for (n, level, amount) in [
+            (1, 3, 1000),
+            (2, 3, 500),
+            (3, 4, 200),
+            (4, 5, 50),
+            (5, 4, 300),
+        ] {
+            let event = ghost_core::JackedIn {
+                user: user(n),
+                amount: tokens(amount),
+                level,
+                newTotal: tokens(amount),
+            };
+            positions
+                .handle_jacked_in(event, meta(&clock))
+                .await
+                .unwrap();
+        }
+
+        // A yield multiplier weights the stake added while it is active
+        let expiry = (clock.now() + TimeDelta::days(1))
+            .timestamp()
+            .cast_unsigned();
+        let multiplier = |n, value_bps| ghost_core::BoostApplied {
+            user: user(n),
+            boostType: u8::from(BoostType::YieldMultiplier),
+            valueBps: value_bps,
+            expiry,
+        };
+        positions
+            .handle_boost_applied(multiplier(2, 2000), meta(&clock))
+            .await
+            .unwrap();
+
+        let stake = ghost_core::StakeAdded {
+            user: user(2),
+            amount: tokens(250),
+            newTotal: tokens(750),
+        };
+        positions
+            .handle_stake_added(stake, meta(&clock))
+            .await
+            .unwrap();
+
+        let extract = ghost_core::Extracted {
+            user: user(1),
+            amount: tokens(1000),
+            rewards: tokens(40),
+        };
+        positions
+            .handle_extracted(extract, meta(&clock))
+            .await
+            .unwrap();
+
+        let cull = ghost_core::PositionCulled {
+            victim: user(4),
+            penaltyAmount: tokens(5),
+            returnedAmount: tokens(45),
+            newEntrant: user(9),
+        };
+        positions
+            .handle_position_culled(cull, meta(&clock))
+            .await
+            .unwrap();
+
+        // Re-entry at another level supersedes the Darknet position
+        let reentry = ghost_core::JackedIn {
+            user: user(5),
+            amount: tokens(300),
+            level: 2,
+            newTotal: tokens(300),
+        };
+        positions
+            .handle_jacked_in(reentry, meta(&clock))
+            .await
+            .unwrap();
+
+        let scan_time = (clock.now() - TimeDelta::hours(1))
+            .timestamp()
+            .cast_unsigned();
+        let executed = trace_scan::ScanExecuted {
+            level: 4,
+            scanId: U256::from(1),
+            seed: U256::from(42),
+            executedAt: scan_time,
+        };
+        scans
+            .handle_scan_executed(executed, meta(&clock))
+            .await
+            .unwrap();
+        let finalized = trace_scan::ScanFinalized {
+            level: 4,
+            scanId: U256::from(1),
+            deathCount: U256::from(2),
+            totalDead: tokens(100),
+            finalizedAt: scan_time,
+        };
+        scans
+            .handle_scan_finalized(finalized, meta(&clock))
+            .await
+            .unwrap();
+
+        let cascade = ghost_core::CascadeDistributed {
+            sourceLevel: 4,
+            sameLevelAmount: tokens(30),
+            upstreamAmount: tokens(30),
+            burnAmount: tokens(30),
+            protocolAmount: tokens(10),
+        };
+        deaths
+            .handle_cascade_distributed(cascade, meta(&clock))
+            .await
+            .unwrap();
+
+        let burn = data_token::Transfer {
+            from: user(3),
+            to: "0x000000000000000000000000000000000000dEaD"
+                .parse()
+                .unwrap(),
+            value: tokens(12),
+        };
+        token.handle_transfer(burn, meta(&clock)).await.unwrap();
+
+        clock.advance(TimeDelta::minutes(5));
+        let reset = ghost_core::SystemResetTriggered {
+            totalPenalty: tokens(100),
+            jackpotWinner: user(2),
+            jackpotAmount: tokens(50),
+        };
+        deaths
+            .handle_system_reset(reset, meta(&clock))
+            .await
+            .unwrap();
+
+        let late = ghost_core::JackedIn {
+            user: user(6),
+            amount: tokens(80),
+            level: 1,
+            newTotal: tokens(80),
+        };
+        positions
+            .handle_jacked_in(late, meta(&clock))
+            .await
+            .unwrap();
+        positions
+            .handle_boost_applied(multiplier(6, 2500), meta(&clock))
+            .await
+            .unwrap();
+
+        aggregator.flush().await.unwrap();
+        let incremental = aggregator.all_level_stats();
+        let incremental_global = aggregator.global_stats();
+
+        // The persisted counters match the in-memory view after a flush
+        for stats in &incremental {
+            let mut persisted = store.persisted(stats.level);
+            persisted.deaths_24h = store.deaths_24h(stats.level);
+            assert_eq!(counters(&persisted), counters(stats), "{:?}", stats.level);
+        }
+
+        let recomputed = aggregator.recompute_from_db().await.unwrap();
+        assert_eq!(recomputed.len(), incremental.len());
+        for (before, after) in incremental.iter().zip(&recomputed) {
+            assert_eq!(before.level, after.level);
+            assert_eq!(counters(before), counters(after), "{:?}", before.level);
+        }
+
+        let global = store.get_global_stats().await.unwrap();
+        assert_eq!(
+            incremental_global.total_value_locked,
+            global.total_value_locked
+        );
+        assert_eq!(incremental_global.total_positions, global.total_positions);
+        assert_eq!(incremental_global.total_deaths, global.total_deaths);
+        assert_eq!(incremental_global.total_burned.to_string(), "12");
+        assert_eq!(incremental_global.system_reset_count, 1);
+
+        // Sanity-check a few absolute values
+        let subnet = aggregator.level_stats(Level::Subnet);
+        assert_eq!(subnet.alive_count, 0);
+        assert_eq!(subnet.total_deaths, 2);
+        assert_eq!(subnet.total_extracted, 1);
+        assert_eq!(subnet.highest_ghost_streak.value(), 7);
+        let darknet = aggregator.level_stats(Level::Darknet);
+        assert_eq!(darknet.total_deaths, 3);
+        assert_eq!(darknet.deaths_24h, 3);
+        assert_eq!(darknet.total_burned.to_string(), "30");
+        assert_eq!(darknet.total_distributed.to_string(), "60");
+        let vault = aggregator.level_stats(Level::Vault);
+        assert_eq!(vault.alive_count, 1);
+        assert_eq!(vault.total_staked.to_string(), "80");
+        assert_eq!(vault.total_effective_staked.to_string(), "100");
+    }
+
+    #[tokio::test]
+    async fn flush_writes_one_update_per_level() {
+        let (aggregator, store, cache, clock) = setup(usize::MAX);
+        let now = clock.now();
+
+        aggregator.record_level(Level::Subnet, staked_delta(100), now);
+        aggregator.record_level(Level::Subnet, staked_delta(50), now);
+        aggregator.record_level(Level::Subnet, staked_delta(25), now);
+        aggregator.record_level(Level::Darknet, staked_delta(10), now);
+        assert_eq!(aggregator.pending(), 4);
+
+        aggregator.flush().await.unwrap();
+
+        assert_eq!(aggregator.pending(), 0);
+        assert_eq!(store.level_updates.load(Ordering::SeqCst), 2);
+        let subnet = store.persisted(Level::Subnet);
+        assert_eq!(subnet.total_staked.to_string(), "175");
+        assert_eq!(subnet.alive_count, 3);
+        assert!(cache.get_global_stats().is_some());
+
+        // Nothing pending, nothing written
+        aggregator.flush().await.unwrap();
+        assert_eq!(store.level_updates.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn failed_flush_requeues_deltas() {
+        let (aggregator, store, _cache, clock) = setup(usize::MAX);
+        let now = clock.now();
+
+        aggregator.record_level(Level::Subnet, staked_delta(100), now);
+        aggregator.record_global(GlobalStatsDelta {
+            system_resets_delta: Some(1),
+            ..GlobalStatsDelta::default()
+        });
+
+        store.fail_updates.store(true, Ordering::SeqCst);
+        assert!(aggregator.flush().await.is_err());
+        assert!(aggregator.pending() > 0);
+
+        aggregator.record_level(Level::Subnet, staked_delta(20), now);
+        store.fail_updates.store(false, Ordering::SeqCst);
+        aggregator.flush().await.unwrap();
+
+        assert_eq!(aggregator.pending(), 0);
+        assert_eq!(
+            store.persisted(Level::Subnet).total_staked.to_string(),
+            "120"
+        );
+        assert_eq!(
+            store.get_global_stats().await.unwrap().system_reset_count,
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn scans_snapshot_the_level_after_their_deaths() {
+        let (aggregator, store, _cache, clock) = setup(usize::MAX);
+        let died = LevelStatsDelta {
+            deaths_delta: Some(1),
+            alive_delta: Some(-1),
+            unstaked_delta: Some(TokenAmount::from_wei(tokens(100), 18)),
+            ..LevelStatsDelta::default()
+        };
+
+        for amount in [100, 50, 25] {
+            aggregator.record_level(Level::Darknet, staked_delta(amount), clock.now());
+        }
+        aggregator.record_level(Level::Darknet, died, clock.now());
+        aggregator.record_scan(Level::Darknet, "7", 1, BlockNumber::new(42), clock.now());
+        store.fail_updates.store(true, Ordering::SeqCst);
+        assert!(aggregator.flush().await.is_err());
+
+        clock.advance(TimeDelta::hours(2));
+        aggregator.record_scan(Level::Darknet, "8", 0, BlockNumber::new(43), clock.now());
+        store.fail_updates.store(false, Ordering::SeqCst);
+        aggregator.flush().await.unwrap();
+
+        let history = store.scan_history.lock().unwrap().clone();
+        let ids: Vec<_> = history.iter().map(|scan| scan.scan_id.as_str()).collect();
+        assert_eq!(ids, ["7", "8"]);
+        assert_eq!((history[0].deaths, history[0].survivors), (1, 2));
+        assert_eq!(history[0].total_staked.to_string(), "75");
+        assert_eq!(history[0].block_number, Some(BlockNumber::new(42)));
+
+        let series = store
+            .get_level_history(
+                Level::Darknet,
+                start_time(),
+                clock.now() + TimeDelta::hours(1),
+                HistoryBucket::Hour,
+            )
+            .await
+            .unwrap();
+        let rates: Vec<_> = series.iter().map(|point| point.death_rate).collect();
+        assert_eq!(rates, [Some(1.0 / 3.0), None, Some(0.0)]);
+    }
+
+    #[tokio::test]
+    async fn threshold_wakes_flush_task() {
+        let (aggregator, store, _cache, clock) = setup(2);
+        let shutdown = CancellationToken::new();
+        let handle = aggregator.spawn_flush_task(shutdown.clone());
+
+        // Let the first (immediate) interval tick pass
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert_eq!(store.level_updates.load(Ordering::SeqCst), 0);
+
+        aggregator.record_level(Level::Subnet, staked_delta(1), clock.now());
+        aggregator.record_level(Level::Subnet, staked_delta(1), clock.now());
+
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while store.level_updates.load(Ordering::SeqCst) == 0 {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+        })
+        .await
+        .unwrap();
+
+        // Final flush on shutdown
+        aggregator.record_level(Level::Darknet, staked_delta(1), clock.now());
+        shutdown.cancel();
+        handle.await.unwrap();
+        assert_eq!(store.persisted(Level::Darknet).alive_count, 1);
+    }
+
+    #[tokio::test]
+    async fn deaths_age_out_of_rolling_window() {
+        let (aggregator, _store, _cache, clock) = setup(usize::MAX);
+        let died = LevelStatsDelta {
+            deaths_delta: Some(3),
+            ..LevelStatsDelta::default()
+        };
+        aggregator.record_level(Level::BlackIce, died, clock.now());
+        assert_eq!(aggregator.level_stats(Level::BlackIce).deaths_24h, 3);
+
+        clock.advance(TimeDelta::hours(25));
+        let stats = aggregator.level_stats(Level::BlackIce);
+        assert_eq!(stats.deaths_24h, 0);
+        assert_eq!(stats.total_deaths, 3);
+
+        // Deaths older than the window at recording time only count towards the total
+        let backfilled = LevelStatsDelta {
+            deaths_delta: Some(1),
+            ..LevelStatsDelta::default()
+        };
+        aggregator.record_level(Level::BlackIce, backfilled, start_time());
+        let stats = aggregator.level_stats(Level::BlackIce);
+        assert_eq!(stats.deaths_24h, 0);
+        assert_eq!(stats.total_deaths, 4);
+    }
+
+    #[tokio::test]
+    async fn load_replays_unflushed_deltas() {
+        let (aggregator, store, _cache, clock) = setup(usize::MAX);
+        store
+            .update_level_stats(Level::Mainframe, staked_delta(500))
+            .await
+            .unwrap();
+
+        aggregator.record_level(Level::Mainframe, staked_delta(20), clock.now());
+        aggregator.load().await.unwrap();
+
+        let stats = aggregator.level_stats(Level::Mainframe);
+        assert_eq!(stats.total_staked.to_string(), "520");
+        assert_eq!(stats.alive_count, 2);
+    }
+
+    #[tokio::test]
+    async fn token_events_maintain_flow_aggregates() {
+        let (aggregator, store, cache, clock) = setup(usize::MAX);
+        let token = TokenHandler::new(Arc::clone(&cache))
+            .with_stats(aggregator.clone())
+            .with_transfer_log(store.clone(), &TokenFlowSettings::default());
+        let dead: Address = "0x000000000000000000000000000000000000dEaD"
+            .parse()
+            .unwrap();
+        let treasury = user(0xee);
+
+        // Two hours ago: a taxed 100 DATA transfer from user 1 to user 2
+        clock.advance(TimeDelta::hours(-2));
+        let taxed = [
+            (user(1), user(2), 90),
+            (user(1), dead, 9),
+            (user(1), treasury, 1),
+        ];
+        for (from, to, amount) in taxed {
+            let event = data_token::Transfer {
+                from,
+                to,
+                value: tokens(amount),
+            };
+            token.handle_transfer(event, meta(&clock)).await.unwrap();
+        }
+        let tax_burned = data_token::TaxBurned {
+            from: user(1),
+            amount: tokens(9),
+        };
+        token
+            .handle_tax_burned(tax_burned, meta(&clock))
+            .await
+            .unwrap();
+        let tax_collected = data_token::TaxCollected {
+            from: user(1),
+            amount: tokens(1),
+        };
+        token
+            .handle_tax_collected(tax_collected, meta(&clock))
+            .await
+            .unwrap();
+
+        // Now: user 2 burns 30 DATA directly and sends 10 back to user 1
+        clock.advance(TimeDelta::hours(2));
+        token
+            .handle_transfer(
+                data_token::Transfer {
+                    from: user(2),
+                    to: dead,
+                    value: tokens(30),
+                },
+                meta(&clock),
+            )
+            .await
+            .unwrap();
+        token
+            .handle_transfer(
+                data_token::Transfer {
+                    from: user(2),
+                    to: user(1),
+                    value: tokens(10),
+                },
+                meta(&clock),
+            )
+            .await
+            .unwrap();
+
+        aggregator.flush().await.unwrap();
+
+        // Burns are counted once, tax burns are tracked separately
+        assert_eq!(aggregator.global_stats().total_burned.to_string(), "39");
+        assert_eq!(store.flows.lock().unwrap().len(), 2);
+
+        let day = store
+            .get_burn_rate(Duration::from_secs(86_400))
+            .await
+            .unwrap();
+        assert_eq!(day.burned.to_string(), "39");
+        assert_eq!(day.tax_burned.to_string(), "9");
+        assert_eq!(day.tax_collected.to_string(), "1");
+        assert_eq!(day.burned_per_hour.to_string(), "1.625");
+
+        // The last hour only sees the direct burn
+        let hour = store
+            .get_burn_rate(Duration::from_secs(3600))
+            .await
+            .unwrap();
+        assert_eq!(hour.burned.to_string(), "30");
+        assert_eq!(hour.tax_burned, TokenAmount::zero());
+        assert_eq!(hour.burned_per_hour.to_string(), "30");
+
+        let sender = store
+            .get_address_flows(&EthAddress::from(user(1)), Duration::from_secs(86_400))
+            .await
+            .unwrap();
+        assert_eq!(sender.outflow.to_string(), "100");
+        assert_eq!(sender.inflow.to_string(), "10");
+        assert_eq!(sender.net_outflow().to_string(), "90");
+        assert_eq!((sender.transfers_in, sender.transfers_out), (1, 3));
+
+        let recipient = store
+            .get_address_flows(&EthAddress::from(user(2)), Duration::from_secs(3600))
+            .await
+            .unwrap();
+        assert_eq!(recipient.inflow, TokenAmount::zero());
+        assert_eq!(recipient.outflow.to_string(), "40");
+
+        // Raw transfers are only persisted when enabled
+        assert!(store.transfers.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn failed_flow_flush_requeues_buckets() {
+        let (aggregator, store, _cache, clock) = setup(usize::MAX);
+        let burn = |amount| TokenFlowDelta {
+            burned: Some(TokenAmount::from_wei(tokens(amount), 18)),
+            ..TokenFlowDelta::default()
+        };
+
+        aggregator.record_token_flow(burn(5), clock.now());
+        store.fail_updates.store(true, Ordering::SeqCst);
+        assert!(aggregator.flush().await.is_err());
+
+        aggregator.record_token_flow(burn(7), clock.now());
+        store.fail_updates.store(false, Ordering::SeqCst);
+        aggregator.flush().await.unwrap();
+
+        assert_eq!(aggregator.pending(), 0);
+        let rate = store
+            .get_burn_rate(Duration::from_secs(3600))
+            .await
+            .unwrap();
+        assert_eq!(rate.burned.to_string(), "12");
+    }
+}
@@ -0,0 +1,311 @@
+//! Transaction context for indexed events.
+//!
+//! Event logs say what happened, not which call made it happen: a `JackedIn`
+//! may come from `GhostCore.jackIn` or from a router batching several calls.
+//! [`TxContextResolver`] fetches the transaction behind an event, matches the
+//! 4-byte selector of its input against the GHOSTNET functions declared in
+//! [`crate::abi`], and records the function and sender on the
+//! [`EventMetadata`].
+//!
+//! This costs one `eth_getTransactionByHash` per transaction, so it is
+//! optional (see [`TxContextSettings`]). Lookups are cached by transaction
+//! hash, since the logs of one transaction share its context.
+
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use alloy::consensus::Transaction as _;
+use alloy::network::TransactionResponse as _;
+use alloy::primitives::{Address, B256, Selector};
+use alloy::providers::{DynProvider, Provider};
+use moka::future::Cache as MokaCache;
+use moka::policy::EvictionPolicy;
+use tracing::{debug, warn};
+
+use crate::abi::selectors;
+use crate::config::TxContextSettings;
+use crate::error::{InfraError, Result};
+use crate::types::events::EventMetadata;
+
+/// Selector → `Contract.function`, for every function in
+/// [`selectors::FUNCTIONS`].
+static SELECTOR_TABLE: LazyLock<HashMap<Selector, String>> = LazyLock::new(|| {
+    selectors::FUNCTIONS
+        .iter()
+        .map(|known| (known.id.into(), format!("{}.{}", known.contract, known.name())))
+        .collect()
+});
+
+/// Look up the GHOSTNET function for a 4-byte selector, e.g.
+/// `"GhostCore.jackIn"`.
+#[must_use]
+pub fn function_name(selector: Selector) -> Option<&'static str> {
+    SELECTOR_TABLE.get(&selector).map(String::as_str)
+}
+
+/// Context of the transaction that emitted an event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxContext {
+    /// GHOSTNET function called, if the selector is known.
+    pub function: Option<String>,
+    /// Sender of the transaction.
+    pub from: Address,
+}
+
+/// Fetches and decodes the transactions behind indexed events.
+pub struct TxContextResolver {
+    /// RPC provider used to fetch transactions.
+    provider: DynProvider,
+    /// Resolved contexts, keyed by transaction hash.
+    cache: MokaCache<B256, TxContext>,
+}
+
+impl std::fmt::Debug for TxContextResolver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TxContextResolver")
+            .field(
+                "cache",
+                &format!("<Cache entries={}>", self.cache.entry_count()),
+            )
+            .finish_non_exhaustive()
+    }
+}
+
+impl TxContextResolver {
+    /// Create a resolver caching up to `cache_capacity` transactions, least
+    /// recently used first out.
+    #[must_use]
+    pub fn new<P>(provider: P, cache_capacity: u64) -> Self
+    where
+        P: Provider + 'static,
+    {
+        let cache = MokaCache::builder()
+            .max_capacity(cache_capacity)
+            .eviction_policy(EvictionPolicy::lru())
+            .build();
+
+        Self {
+            provider: provider.erased(),
+            cache,
+        }
+    }
+
+    /// Create a resolver from settings, or `None` when transaction context
+    /// is disabled.
+    #[must_use]
+    pub fn from_settings<P>(provider: P, settings: &TxContextSettings) -> Option<Self>
+    where
+        P: Provider + 'static,
+    {
+        settings
+            .enabled
+            .then(|| Self::new(provider, settings.cache_capacity))
+    }
+
+    /// Resolve the context of a transaction.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the RPC request fails or the transaction is not
+    /// found. Failed lookups are not cached.
+    pub async fn resolve(&self, tx_hash: B256) -> Result<TxContext> {
+        if let Some(context) = self.cache.get(&tx_hash).await {
+            debug!(%tx_hash, "Transaction context cache hit");
+            return Ok(context);
+        }
+
+        let tx = self
+            .provider
+            .get_transaction_by_hash(tx_hash)
+            .await
+            .map_err(|e| InfraError::Rpc(Box::new(e)))?
+            .ok_or_else(|| {
+                InfraError::EventDecoding(format!("Transaction not found: {tx_hash}"))
+            })?;
+
+        let function = tx
+            .input()
+            .get(..4)
+            .map(Selector::from_slice)
+            .and_then(function_name)
+            .map(str::to_string);
+        let context = TxContext {
+            function,
+            from: tx.from(),
+        };
+
+        self.cache.insert(tx_hash, context.clone()).await;
+        Ok(context)
+    }
+
+    /// Attach the context of the event's transaction to `meta`.
+    ///
+    /// Best effort: if the transaction cannot be resolved the fields stay
+    /// `None` and the event is indexed without them.
+    pub async fn enrich(&self, meta: &mut EventMetadata) {
+        match self.resolve(meta.tx_hash).await {
+            Ok(context) => {
+                meta.tx_function = context.function;
+                meta.tx_from = Some(context.from);
+            }
+            Err(e) => {
+                warn!(tx_hash = %meta.tx_hash, error = %e, "Failed to resolve transaction context");
+            }
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use alloy::consensus::{Signed, TxEnvelope, TxLegacy};
+    use alloy::primitives::{Bytes, Signature, U256};
+    use alloy::providers::ProviderBuilder;
+    use alloy::rpc::types::Transaction;
+    use alloy::sol_types::SolCall;
+    use alloy::transports::mock::Asserter;
+    use chrono::Utc;
+
+    use crate::abi::{ghost_core, hash_crash, rewards_distributor};
+
+    use super::*;
+
+    fn sender() -> Address {
+        Address::repeat_byte(0x42)
+    }
+
+    /// An RPC transaction from [`sender`] with the given input.
+    fn transaction(input: Bytes) -> Transaction {
+        let tx = TxLegacy {
+            input,
+            ..TxLegacy::default()
+        };
+        let signed = Signed::new_unchecked(tx, Signature::test_signature(), B256::ZERO);
+        Transaction {
+            inner: alloy::consensus::transaction::Recovered::new_unchecked(
+                TxEnvelope::Legacy(signed),
+                sender(),
+            ),
+            block_hash: None,
+            block_number: None,
+            transaction_index: None,
+            effective_gas_price: None,
+        }
+    }
+
+    fn jack_in_input() -> Bytes {
+        ghost_core::jackInCall {
+            amount: U256::from(100),
+            level: 3,
+        }
+        .abi_encode()
+        .into()
+    }
+
+    fn resolver(asserter: &Asserter) -> TxContextResolver {
+        let provider = ProviderBuilder::new().connect_mocked_client(asserter.clone());
+        TxContextResolver::new(provider, 100)
+    }
+
+    #[test]
+    fn registry_names_every_function() {
+        assert_eq!(SELECTOR_TABLE.len(), 33, "selectors must not collide");
+        assert_eq!(
+            function_name(ghost_core::jackInCall::SELECTOR.into()),
+            Some("GhostCore.jackIn")
+        );
+        assert_eq!(
+            function_name(rewards_distributor::claimCall::SELECTOR.into()),
+            Some("TeamVesting.claim")
+        );
+        assert_eq!(
+            function_name(hash_crash::placeBetCall::SELECTOR.into()),
+            Some("HashCrash.placeBet")
+        );
+        assert_eq!(function_name(Selector::ZERO), None);
+    }
+
+    #[tokio::test]
+    async fn resolves_function_and_sender_once_per_tx() {
+        let asserter = Asserter::new();
+        asserter.push_success(&transaction(jack_in_input()));
+        let resolver = resolver(&asserter);
+        let tx_hash = B256::repeat_byte(1);
+
+        let context = resolver.resolve(tx_hash).await.unwrap();
+        assert_eq!(context.function.as_deref(), Some("GhostCore.jackIn"));
+        assert_eq!(context.from, sender());
+
+        // A second log of the same transaction is served from the cache; the
+        // asserter has no response left, so another request would fail
+        assert_eq!(resolver.resolve(tx_hash).await.unwrap(), context);
+        assert!(resolver.resolve(B256::repeat_byte(2)).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn unknown_selectors_keep_the_sender() {
+        let asserter = Asserter::new();
+        // A router call, and a plain ETH transfer without input
+        asserter.push_success(&transaction(Bytes::from_static(&[
+            0xde, 0xad, 0xbe, 0xef, 0,
+        ])));
+        asserter.push_success(&transaction(Bytes::new()));
+        let resolver = resolver(&asserter);
+
+        for tx_hash in [B256::repeat_byte(1), B256::repeat_byte(2)] {
+            let context = resolver.resolve(tx_hash).await.unwrap();
+            assert_eq!(context.function, None);
+            assert_eq!(context.from, sender());
+        }
+    }
+
+    #[tokio::test]
+    async fn enrich_leaves_metadata_alone_on_failure() {
+        let asserter = Asserter::new();
+        asserter.push_success(&transaction(jack_in_input()));
+        asserter.push_failure_msg("node unavailable");
+        let resolver = resolver(&asserter);
+
+        let mut meta = EventMetadata {
+            block_number: 1,
+            block_hash: B256::ZERO,
+            tx_hash: B256::repeat_byte(1),
+            tx_index: 0,
+            log_index: 0,
+            timestamp: Utc::now(),
+            contract: Address::ZERO,
+            chain_id: 6343,
+            tx_function: None,
+            tx_from: None,
+        };
+        resolver.enrich(&mut meta).await;
+        assert_eq!(meta.tx_function.as_deref(), Some("GhostCore.jackIn"));
+        assert_eq!(meta.tx_from, Some(sender()));
+
+        let mut other = EventMetadata {
+            tx_hash: B256::repeat_byte(2),
+            tx_function: None,
+            tx_from: None,
+            ..meta
+        };
+        resolver.enrich(&mut other).await;
+        assert_eq!((other.tx_function, other.tx_from), (None, None));
+    }
+
+    #[test]
+    fn disabled_settings_build_no_resolver() {
+        let provider = ProviderBuilder::new().connect_mocked_client(Asserter::new());
+        let disabled = TxContextSettings::default();
+        assert!(TxContextResolver::from_settings(provider.clone(), &disabled).is_none());
+
+        let enabled = TxContextSettings {
+            enabled: true,
+            ..disabled
+        };
+        assert!(TxContextResolver::from_settings(provider, &enabled).is_some());
+    }
+}
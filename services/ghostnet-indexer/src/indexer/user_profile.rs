@@ -0,0 +1,797 @@
+//! User profile documents.
+//!
+//! A profile page shows a user's position, position history, `DeadPool`
+//! bets, cascade earnings and token flows. [`UserProfileService`] reads all
+//! of them at once and composes them into one [`UserProfile`]:
+//!
+//! ```text
+//! GET /users/:address ──▶ UserProfileService ──▶ cache (30s)
+//!                                │ miss
+//!                                ├──▶ PositionStore::get_active_position
+//!                                ├──▶ PositionStore::get_history
+//!                                ├──▶ MarketStore::get_user_bets        (concurrently,
+//!                                ├──▶ DeathStore::get_cascade_earnings   each with a
+//!                                └──▶ TokenFlowStore::get_address_flows  timeout)
+//! ```
+//!
+//! A section without activity is `null`. A section whose read times out is
+//! `null` too, and listed in `timed_out` with the document marked `partial`;
+//! partial documents are not cached.
+
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use moka::sync::Cache as MokaCache;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use crate::config::UserProfileSettings;
+use crate::error::Result;
+use crate::ports::{DeathStore, MarketStore, PositionStore, TokenFlowStore};
+use crate::types::entities::{AddressFlows, Bet, Position, PositionAction, PositionHistoryEntry};
+use crate::types::primitives::{BlockNumber, EthAddress, GhostStreak, TokenAmount};
+
+/// Most profile documents cached at once.
+const CACHE_MAX_CAPACITY: u64 = 10_000;
+
+/// Basis points of a whole.
+const BPS: u64 = 10_000;
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// PROFILE DOCUMENT
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Everything indexed about one user.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserProfile {
+    /// The user's address.
+    pub address: EthAddress,
+    /// The user's active position.
+    pub position: Option<CurrentPosition>,
+    /// Summary of the user's recent position history.
+    pub history: Option<PositionHistorySummary>,
+    /// Summary of the user's recent `DeadPool` bets.
+    pub dead_pool: Option<BettingSummary>,
+    /// Cascade rewards the user earned from other players' deaths, in total.
+    pub cascade_earnings: Option<TokenAmount>,
+    /// DATA the user received and sent over the flow window.
+    pub token_flows: Option<TokenFlowSummary>,
+    /// Whether any section timed out and was left out.
+    pub partial: bool,
+    /// Sections that timed out.
+    pub timed_out: Vec<ProfileSection>,
+}
+
+/// Section of a [`UserProfile`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProfileSection {
+    /// [`UserProfile::position`].
+    Position,
+    /// [`UserProfile::history`].
+    History,
+    /// [`UserProfile::dead_pool`].
+    DeadPool,
+    /// [`UserProfile::cascade_earnings`].
+    CascadeEarnings,
+    /// [`UserProfile::token_flows`].
+    TokenFlows,
+}
+
+/// Active position with what the indexer knows of its value.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CurrentPosition {
+    /// The position.
+    #[serde(flatten)]
+    pub position: Position,
+    /// Seconds since the position was entered.
+    pub held_secs: i64,
+    /// Finalized scans at the position's level since it was entered.
+    pub scans_survived: u32,
+}
+
+/// Summary of a user's position history.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PositionHistorySummary {
+    /// History entries summarized, newest first.
+    pub entries: u32,
+    /// Whether the summary covers the whole history rather than its most
+    /// recent entries.
+    pub complete: bool,
+    /// Positions entered.
+    pub positions: u32,
+    /// Positions voluntarily extracted.
+    pub extractions: u32,
+    /// Positions traced in a scan or culled.
+    pub deaths: u32,
+    /// Stake taken out by extractions.
+    pub total_extracted: TokenAmount,
+    /// Highest ghost streak reached.
+    pub best_streak: GhostStreak,
+}
+
+impl PositionHistorySummary {
+    /// Summary of `entries`, which are the whole history unless `limit` of
+    /// them were returned. `None` without entries.
+    #[must_use]
+    pub fn of(entries: &[PositionHistoryEntry], limit: u32) -> Option<Self> {
+        if entries.is_empty() {
+            return None;
+        }
+        let mut summary = Self {
+            entries: u32::try_from(entries.len()).unwrap_or(u32::MAX),
+            complete: entries.len() < limit as usize,
+            positions: 0,
+            extractions: 0,
+            deaths: 0,
+            total_extracted: TokenAmount::zero(),
+            best_streak: GhostStreak::ZERO,
+        };
+        for entry in entries {
+            match entry.action {
+                PositionAction::JackedIn => summary.positions += 1,
+                PositionAction::Extracted => {
+                    summary.extractions += 1;
+                    summary.total_extracted =
+                        summary.total_extracted.saturating_add(&entry.amount_change);
+                }
+                PositionAction::Traced | PositionAction::Culled => summary.deaths += 1,
+                _ => {}
+            }
+            summary.best_streak = summary.best_streak.max(entry.ghost_streak);
+        }
+        Some(summary)
+    }
+}
+
+/// Summary of a user's `DeadPool` bets.
+///
+/// Bets count as won once their winnings are claimed, so unclaimed wins
+/// show up as losses until then.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BettingSummary {
+    /// Bets summarized, newest first.
+    pub bets: u32,
+    /// Bets whose winnings were claimed.
+    pub bets_won: u32,
+    /// Share of the bets won, in basis points.
+    pub win_rate_bps: u64,
+    /// DATA wagered.
+    pub total_wagered: TokenAmount,
+    /// Winnings claimed.
+    pub total_winnings: TokenAmount,
+    /// Amount by which winnings exceed the wagers.
+    pub net_won: TokenAmount,
+    /// Amount by which the wagers exceed winnings.
+    pub net_lost: TokenAmount,
+}
+
+impl BettingSummary {
+    /// Summary of `bets`, `None` without any.
+    #[must_use]
+    pub fn of(bets: &[Bet]) -> Option<Self> {
+        if bets.is_empty() {
+            return None;
+        }
+        let mut bets_won = 0_u32;
+        let mut total_wagered = TokenAmount::zero();
+        let mut total_winnings = TokenAmount::zero();
+        for bet in bets {
+            total_wagered = total_wagered.saturating_add(&bet.amount);
+            if let Some(winnings) = &bet.winnings {
+                bets_won += 1;
+                total_winnings = total_winnings.saturating_add(winnings);
+            }
+        }
+        let count = u32::try_from(bets.len()).unwrap_or(u32::MAX);
+        Some(Self {
+            bets: count,
+            bets_won,
+            win_rate_bps: u64::from(bets_won) * BPS / u64::from(count),
+            net_won: total_winnings.saturating_sub(&total_wagered),
+            net_lost: total_wagered.saturating_sub(&total_winnings),
+            total_wagered,
+            total_winnings,
+        })
+    }
+}
+
+/// Token flows of a user with their net direction.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenFlowSummary {
+    /// Raw inflow and outflow totals.
+    #[serde(flatten)]
+    pub flows: AddressFlows,
+    /// Amount by which inflow exceeds outflow.
+    pub net_inflow: TokenAmount,
+    /// Amount by which outflow exceeds inflow.
+    pub net_outflow: TokenAmount,
+}
+
+impl TokenFlowSummary {
+    /// Summary of `flows`, `None` without transfers.
+    #[must_use]
+    pub fn of(flows: AddressFlows) -> Option<Self> {
+        (flows.transfers_in > 0 || flows.transfers_out > 0).then(|| Self {
+            net_inflow: flows.net_inflow(),
+            net_outflow: flows.net_outflow(),
+            flows,
+        })
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// USER PROFILE SERVICE
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Composes [`UserProfile`] documents from the stores.
+pub struct UserProfileService<S> {
+    /// Store every section is read from.
+    store: Arc<S>,
+    /// Complete documents by address.
+    cache: MokaCache<EthAddress, UserProfile>,
+    /// How long each section may take.
+    section_timeout: Duration,
+    /// History entries summarized.
+    history_limit: u32,
+    /// Bets summarized.
+    bets_limit: u32,
+    /// Window of the token flow summary.
+    flow_window: Duration,
+}
+
+impl<S> std::fmt::Debug for UserProfileService<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UserProfileService")
+            .field("section_timeout", &self.section_timeout)
+            .field("history_limit", &self.history_limit)
+            .field("bets_limit", &self.bets_limit)
+            .field("flow_window", &self.flow_window)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S> UserProfileService<S>
+where
+    S: PositionStore + DeathStore + MarketStore + TokenFlowStore,
+{
+    /// Create a service reading from `store`.
+    #[must_use]
+    pub fn new(store: Arc<S>, settings: &UserProfileSettings) -> Self {
+        Self {
+            store,
+            cache: MokaCache::builder()
+                .max_capacity(CACHE_MAX_CAPACITY)
+                .time_to_live(settings.cache_ttl())
+                .build(),
+            section_timeout: settings.section_timeout(),
+            history_limit: settings.history_limit,
+            bets_limit: settings.bets_limit,
+            flow_window: settings.flow_window(),
+        }
+    }
+
+    /// Profile of `address`, from cache if a complete one was composed
+    /// within the cache TTL.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a section's store query fails. Sections that
+    /// time out are left out instead.
+    pub async fn profile(&self, address: &EthAddress) -> Result<UserProfile> {
+        if let Some(profile) = self.cache.get(address) {
+            return Ok(profile);
+        }
+
+        let store = self.store.as_ref();
+        let (position, history, dead_pool, cascade_earnings, token_flows) = tokio::join!(
+            self.section(ProfileSection::Position, self.current_position(address)),
+            self.section(ProfileSection::History, async {
+                let entries = store.get_history(address, self.history_limit, None).await?;
+                Ok(PositionHistorySummary::of(&entries, self.history_limit))
+            }),
+            self.section(ProfileSection::DeadPool, async {
+                let bets = store.get_user_bets(address, self.bets_limit).await?;
+                Ok(BettingSummary::of(&bets))
+            }),
+            self.section(ProfileSection::CascadeEarnings, async {
+                let earnings = store.get_cascade_earnings(address, 1).await?;
+                Ok((!earnings.scans.is_empty()).then_some(earnings.total))
+            }),
+            self.section(ProfileSection::TokenFlows, async {
+                let flows = store.get_address_flows(address, self.flow_window).await?;
+                Ok(TokenFlowSummary::of(flows))
+            }),
+        );
+
+        let mut timed_out = Vec::new();
+        let profile = UserProfile {
+            address: *address,
+            position: position?.take(&mut timed_out),
+            history: history?.take(&mut timed_out),
+            dead_pool: dead_pool?.take(&mut timed_out),
+            cascade_earnings: cascade_earnings?.take(&mut timed_out),
+            token_flows: token_flows?.take(&mut timed_out),
+            partial: !timed_out.is_empty(),
+            timed_out,
+        };
+        if profile.partial {
+            debug!(%address, timed_out = ?profile.timed_out, "Composed partial profile");
+        } else {
+            self.cache.insert(*address, profile.clone());
+        }
+        Ok(profile)
+    }
+
+    /// The active position of `address` with the scans it survived so far.
+    async fn current_position(&self, address: &EthAddress) -> Result<Option<CurrentPosition>> {
+        let Some(position) = self.store.get_active_position(address).await? else {
+            return Ok(None);
+        };
+        let scans_survived = self
+            .store
+            .count_scans_survived(
+                position.level,
+                position.created_at_block,
+                BlockNumber::new(u64::MAX),
+            )
+            .await?;
+        Ok(Some(CurrentPosition {
+            held_secs: (Utc::now() - position.entry_timestamp).num_seconds().max(0),
+            scans_survived,
+            position,
+        }))
+    }
+
+    /// Read one section, giving up on it after the section timeout.
+    async fn section<T>(
+        &self,
+        section: ProfileSection,
+        read: impl Future<Output = Result<Option<T>>>,
+    ) -> Result<Section<T>> {
+        tokio::time::timeout(self.section_timeout, read)
+            .await
+            .map_or_else(
+                |_| {
+                    warn!(?section, timeout = ?self.section_timeout, "Profile section timed out");
+                    Ok(Section::TimedOut(section))
+                },
+                |value| value.map(Section::Read),
+            )
+    }
+}
+
+/// Outcome of reading one profile section.
+enum Section<T> {
+    /// The section, `None` without activity.
+    Read(Option<T>),
+    /// The read timed out.
+    TimedOut(ProfileSection),
+}
+
+impl<T> Section<T> {
+    /// The section's value, noting it in `timed_out` if it timed out.
+    fn take(self, timed_out: &mut Vec<ProfileSection>) -> Option<T> {
+        match self {
+            Self::Read(value) => value,
+            Self::TimedOut(section) => {
+                timed_out.push(section);
+                None
+            }
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use async_trait::async_trait;
+    use chrono::{DateTime, TimeZone};
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::error::InfraError;
+    use crate::types::entities::{
+        BurnRate, CascadeEarnings, CascadeShare, Death, HistoryCursor, OutboxEvent, Page, Pools,
+        PositionFilter, Round, RoundSettlement, ScanCascadeEarnings, TokenFlowDelta, TokenTransfer,
+    };
+    use crate::types::enums::Level;
+
+    const USER: EthAddress = EthAddress::new([0xaa; 20]);
+
+    fn data(amount: &str) -> TokenAmount {
+        TokenAmount::parse(amount).unwrap()
+    }
+
+    fn at(secs: i64) -> DateTime<Utc> {
+        Utc.timestamp_opt(1_700_000_000 + secs, 0).unwrap()
+    }
+
+    fn entry(action: PositionAction, amount: &str, streak: i32) -> PositionHistoryEntry {
+        PositionHistoryEntry {
+            id: Uuid::now_v7(),
+            position_id: Uuid::new_v4(),
+            user_address: USER,
+            action,
+            amount_change: data(amount),
+            new_total: TokenAmount::zero(),
+            level: Level::Darknet,
+            ghost_streak: GhostStreak::new(streak).unwrap(),
+            block_number: BlockNumber::new(100),
+            timestamp: at(0),
+        }
+    }
+
+    fn bet(amount: &str, winnings: Option<&str>) -> Bet {
+        Bet {
+            id: Uuid::new_v4(),
+            round_id: Uuid::new_v4(),
+            user_address: USER,
+            amount: data(amount),
+            is_over: true,
+            block_number: BlockNumber::new(100),
+            log_index: 0,
+            placed_at: at(0),
+            pools_before: Pools::default(),
+            implied_probability_bps: None,
+            payout_multiple_bps: None,
+            is_claimed: winnings.is_some(),
+            winnings: winnings.map(data),
+            claimed_at: None,
+        }
+    }
+
+    fn position() -> Position {
+        Position {
+            id: Uuid::new_v4(),
+            user_address: USER,
+            level: Level::Darknet,
+            amount: data("250"),
+            effective_stake: data("250"),
+            reward_debt: TokenAmount::zero(),
+            entry_timestamp: at(0),
+            last_add_timestamp: None,
+            ghost_streak: GhostStreak::new(4).unwrap(),
+            is_alive: true,
+            is_extracted: false,
+            exit_reason: None,
+            exit_timestamp: None,
+            survival_seconds: None,
+            scans_survived: None,
+            extracted_amount: None,
+            extracted_rewards: None,
+            created_at_block: BlockNumber::new(100),
+            updated_at: at(0),
+        }
+    }
+
+    /// Store serving one user's activity, whose bet reads can be made to
+    /// hang. Counts the active position reads.
+    #[derive(Debug, Default)]
+    struct ProfileStore {
+        position: Option<Position>,
+        history: Vec<PositionHistoryEntry>,
+        bets: Vec<Bet>,
+        cascades: Vec<ScanCascadeEarnings>,
+        transfers: u64,
+        slow_bets: bool,
+        position_reads: AtomicU32,
+    }
+
+    impl ProfileStore {
+        /// A user active in every section.
+        fn active() -> Self {
+            Self {
+                position: Some(position()),
+                history: vec![
+                    entry(PositionAction::JackedIn, "250", 0),
+                    entry(PositionAction::Extracted, "120", 9),
+                    entry(PositionAction::Culled, "100", 3),
+                    entry(PositionAction::JackedIn, "100", 0),
+                    entry(PositionAction::JackedIn, "100", 0),
+                ],
+                bets: vec![bet("10", Some("25")), bet("10", None), bet("20", None)],
+                cascades: vec![ScanCascadeEarnings {
+                    scan_id: Some("7".into()),
+                    source_level: Level::BlackIce,
+                    amount: data("2.5"),
+                    block_number: BlockNumber::new(100),
+                    timestamp: at(0),
+                }],
+                transfers: 3,
+                ..Self::default()
+            }
+        }
+    }
+
+    #[async_trait]
+    impl PositionStore for ProfileStore {
+        async fn get_active_position(&self, _: &EthAddress) -> Result<Option<Position>> {
+            self.position_reads.fetch_add(1, Ordering::Relaxed);
+            Ok(self.position.clone())
+        }
+
+        async fn save_position(&self, _: &Position) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_at_risk_positions(&self, _: Level, _: u32) -> Result<Vec<Position>> {
+            Ok(vec![])
+        }
+
+        async fn append_history(&self, _: &PositionHistoryEntry) -> Result<()> {
+            Ok(())
+        }
+
+        async fn save_position_with_history(
+            &self,
+            _: &Position,
+            _: &PositionHistoryEntry,
+            _: &[OutboxEvent],
+        ) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_history(
+            &self,
+            _: &EthAddress,
+            limit: u32,
+            _: Option<HistoryCursor>,
+        ) -> Result<Vec<PositionHistoryEntry>> {
+            Ok(self.history.iter().take(limit as usize).cloned().collect())
+        }
+
+        async fn get_position_by_id(&self, _: &Uuid) -> Result<Option<Position>> {
+            Ok(None)
+        }
+
+        async fn get_positions_by_level(&self, _: Level) -> Result<Vec<Position>> {
+            Ok(vec![])
+        }
+
+        async fn query(&self, _: &PositionFilter, _: u32) -> Result<Page<Position>> {
+            Ok(Page {
+                items: vec![],
+                next_cursor: None,
+            })
+        }
+
+        async fn count_positions_by_level(&self, _: Level) -> Result<u32> {
+            Ok(0)
+        }
+
+        async fn count_scans_survived(
+            &self,
+            _: Level,
+            _: BlockNumber,
+            _: BlockNumber,
+        ) -> Result<u32> {
+            Ok(4)
+        }
+    }
+
+    #[async_trait]
+    impl MarketStore for ProfileStore {
+        async fn save_round(&self, _: &Round) -> Result<()> {
+            Ok(())
+        }
+
+        async fn record_bet(&self, _: &Bet) -> Result<()> {
+            Ok(())
+        }
+
+        async fn resolve_round(&self, _: &str, _: &RoundSettlement) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_active_rounds(&self, _: u32) -> Result<Vec<Round>> {
+            Ok(vec![])
+        }
+
+        async fn get_round_by_id(&self, _: &str) -> Result<Option<Round>> {
+            Ok(None)
+        }
+
+        async fn get_bets_for_round(&self, _: &str) -> Result<Vec<Bet>> {
+            Ok(vec![])
+        }
+
+        async fn get_user_bets(&self, _: &EthAddress, limit: u32) -> Result<Vec<Bet>> {
+            if self.slow_bets {
+                std::future::pending::<()>().await;
+            }
+            Ok(self.bets.iter().take(limit as usize).cloned().collect())
+        }
+
+        async fn mark_bet_claimed(&self, _: &str, _: &EthAddress, _: &TokenAmount) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl DeathStore for ProfileStore {
+        async fn record_deaths(&self, _: &[Death]) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_deaths_for_scan(&self, _: &str) -> Result<Vec<Death>> {
+            Ok(vec![])
+        }
+
+        async fn get_user_deaths(&self, _: &EthAddress, _: u32) -> Result<Vec<Death>> {
+            Ok(vec![])
+        }
+
+        async fn count_deaths_by_level(&self, _: Level) -> Result<u64> {
+            Ok(0)
+        }
+
+        async fn get_recent_deaths(&self, _: u32) -> Result<Vec<Death>> {
+            Ok(vec![])
+        }
+
+        async fn record_cascade_shares(&self, _: &[CascadeShare]) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_cascade_earnings(
+            &self,
+            _: &EthAddress,
+            limit: u32,
+        ) -> Result<CascadeEarnings> {
+            let total = self
+                .cascades
+                .iter()
+                .fold(TokenAmount::zero(), |total, scan| {
+                    total.saturating_add(&scan.amount)
+                });
+            Ok(CascadeEarnings {
+                total,
+                scans: self.cascades.iter().take(limit as usize).cloned().collect(),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl TokenFlowStore for ProfileStore {
+        async fn record_transfer(&self, _: &TokenTransfer) -> Result<()> {
+            Ok(())
+        }
+
+        async fn record_token_flows(&self, _: DateTime<Utc>, _: &TokenFlowDelta) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_burn_rate(&self, _: Duration) -> Result<BurnRate> {
+            Err(InfraError::NotFound.into())
+        }
+
+        async fn get_address_flows(
+            &self,
+            address: &EthAddress,
+            window: Duration,
+        ) -> Result<AddressFlows> {
+            let active = self.transfers > 0;
+            Ok(AddressFlows {
+                address: *address,
+                window_secs: window.as_secs(),
+                inflow: if active {
+                    data("300")
+                } else {
+                    TokenAmount::zero()
+                },
+                outflow: if active {
+                    data("350")
+                } else {
+                    TokenAmount::zero()
+                },
+                transfers_in: self.transfers.min(1),
+                transfers_out: self.transfers.saturating_sub(1),
+            })
+        }
+    }
+
+    fn service(store: ProfileStore) -> (Arc<ProfileStore>, UserProfileService<ProfileStore>) {
+        let store = Arc::new(store);
+        let settings = UserProfileSettings {
+            section_timeout_ms: 50,
+            history_limit: 4,
+            ..UserProfileSettings::default()
+        };
+        (
+            Arc::clone(&store),
+            UserProfileService::new(store, &settings),
+        )
+    }
+
+    #[tokio::test]
+    async fn composes_every_section() {
+        let (store, service) = service(ProfileStore::active());
+        let profile = service.profile(&USER).await.unwrap();
+
+        assert!(!profile.partial);
+        assert!(profile.timed_out.is_empty());
+        let position = profile.position.unwrap();
+        assert_eq!(position.position.amount, data("250"));
+        assert_eq!(position.scans_survived, 4);
+        assert!(position.held_secs > 0);
+
+        // Only the four most recent entries are summarized
+        let history = profile.history.unwrap();
+        assert_eq!(history.entries, 4);
+        assert!(!history.complete);
+        assert_eq!(history.positions, 2);
+        assert_eq!(history.extractions, 1);
+        assert_eq!(history.deaths, 1);
+        assert_eq!(history.total_extracted, data("120"));
+        assert_eq!(history.best_streak, GhostStreak::new(9).unwrap());
+
+        let dead_pool = profile.dead_pool.unwrap();
+        assert_eq!((dead_pool.bets, dead_pool.bets_won), (3, 1));
+        assert_eq!(dead_pool.win_rate_bps, 3333);
+        assert_eq!(dead_pool.total_wagered, data("40"));
+        assert_eq!(dead_pool.net_lost, data("15"));
+        assert!(dead_pool.net_won.is_zero());
+
+        assert_eq!(profile.cascade_earnings, Some(data("2.5")));
+        let flows = profile.token_flows.unwrap();
+        assert_eq!(flows.flows.window_secs, 2_592_000);
+        assert_eq!(flows.net_outflow, data("50"));
+
+        // Served from cache the second time
+        assert_eq!(service.profile(&USER).await.unwrap().address, USER);
+        assert_eq!(store.position_reads.load(Ordering::Relaxed), 1);
+    }
+
+    #[tokio::test]
+    async fn sections_without_activity_are_null() {
+        let (_, service) = service(ProfileStore {
+            bets: vec![bet("10", Some("30")), bet("10", Some("5"))],
+            ..ProfileStore::default()
+        });
+        let profile = service.profile(&USER).await.unwrap();
+
+        assert!(!profile.partial);
+        assert!(profile.position.is_none());
+        assert!(profile.history.is_none());
+        assert!(profile.cascade_earnings.is_none());
+        assert!(profile.token_flows.is_none());
+        let dead_pool = profile.dead_pool.as_ref().unwrap();
+        assert_eq!(dead_pool.win_rate_bps, 10_000);
+        assert_eq!(dead_pool.net_won, data("15"));
+        assert!(dead_pool.net_lost.is_zero());
+
+        let json = serde_json::to_value(&profile).unwrap();
+        assert!(json["position"].is_null());
+        assert_eq!(json["dead_pool"]["bets"], 2);
+    }
+
+    #[tokio::test]
+    async fn slow_sections_leave_a_partial_document() {
+        let (store, service) = service(ProfileStore {
+            slow_bets: true,
+            ..ProfileStore::active()
+        });
+        let profile = service.profile(&USER).await.unwrap();
+
+        assert!(profile.partial);
+        assert_eq!(profile.timed_out, [ProfileSection::DeadPool]);
+        assert!(profile.dead_pool.is_none());
+        assert!(profile.position.is_some());
+        assert!(profile.history.is_some());
+        let json = serde_json::to_value(&profile).unwrap();
+        assert_eq!(json["timed_out"], serde_json::json!(["dead_pool"]));
+
+        // Partial documents are composed afresh
+        service.profile(&USER).await.unwrap();
+        assert_eq!(store.position_reads.load(Ordering::Relaxed), 2);
+    }
+}
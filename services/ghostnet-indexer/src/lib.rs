@@ -29,12 +29,13 @@
 //! - [`types`] - Domain types (enums, events, entities, primitives)
 //! - [`error`] - Layered error types
 //! - [`config`] - Configuration loading and validation
-//! - [`abi`] - ABI bindings for GHOSTNET contracts
+//! - [`abi`] - ABI bindings for GHOSTNET contracts (the `ghostnet-abi` crate)
 //! - [`indexer`] - Core indexing logic (block processor, event router)
 //! - [`handlers`] - Event handlers for each contract
 //! - [`store`] - Data persistence (`PostgreSQL`, cache)
 //! - [`streaming`] - Apache Iggy integration
 //! - [`api`] - REST and WebSocket API
+//! - [`obs`] - Metrics facade and Prometheus endpoint
 //!
 //! # Getting Started
 //!
@@ -53,19 +54,19 @@
 #![doc(html_root_url = "https://docs.ghostnet.io/indexer")]
 
 // Module declarations - added as each phase completes
-pub mod abi;
+// Shared with the fleet's GHOSTNET plugin
+pub use ghostnet_abi as abi;
+pub mod api;
 pub mod config;
 pub mod error;
 pub mod handlers;
 pub mod indexer;
+pub mod obs;
 pub mod ports;
 pub mod store;
 pub mod streaming;
 pub mod types;
 
-// Future modules (uncomment as implemented):
-// pub mod api;
-
 /// Library version
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 
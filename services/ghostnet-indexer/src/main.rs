@@ -3,10 +3,89 @@
 //! Entry point for the indexer binary. Provides subcommands for:
 //! - `run` - Start the indexer
 //! - `migrate` - Run database migrations
-//! - `backfill` - Backfill historical data
+//! - `backfill` - Backfill historical data, e.g. of a newly enabled contract
+//! - `recompute-stats` - Rebuild aggregate stats from the raw tables
+//! - `verify` - Cross-check indexed state against the contracts
+//! - `replay` - Run handlers again over the archived raw logs
+//! - `quarantine` - List and retry events quarantined by failing handlers
+//!
+//! On `run`, contracts whose cursor is far behind the others catch up on
+//! their own processor while the rest keep indexing new blocks. A contract
+//! that has never been indexed is skipped until it is backfilled with
+//! `backfill --contract <name> --from <block>`.
+//!
+//! With `outbox.enabled`, position events are written to the event outbox
+//! with the position change and published by the outbox relay, which stops
+//! after the pipeline so events committed during the drain still go out.
+//! Events a backfill writes to the outbox are published on the next `run`.
+//!
+//! `verify` exits with status 2 when it finds more mismatches than
+//! `--max-mismatches`, so it can alert from cron. With `--fix`, every value
+//! it overwrites is appended to the `--journal` file as a line of JSON.
+//!
+//! With `[[chains]]` configured, `run` indexes every enabled chain side by
+//! side, each into its own database schema, and `migrate` creates and
+//! migrates the schemas. The other commands work on the primary chain, or
+//! on the one given with `--chain`.
+//!
+//! `replay` routes the logs archived with `raw_logs.enabled` through the
+//! handlers named by `--handlers`, without the outbox. It resumes where a
+//! stopped run of the same range and handlers left off. The position and
+//! death handlers only replay with `--truncate-derived`, over the whole
+//! archive, after which the aggregate stats are recomputed. Stop the indexer
+//! while replaying.
+//!
+//! With `quarantine.enabled`, a log whose handler keeps failing is
+//! quarantined and indexing carries on. `quarantine list` shows what was
+//! quarantined and why; `quarantine retry <id>` and `quarantine retry-all`
+//! route quarantined logs through the handlers again once they are fixed,
+//! writing to the outbox as `run` does.
+
+use std::io::Write;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
 
+use alloy::primitives::Address;
+use alloy::providers::{Provider, ProviderBuilder};
 use clap::{Parser, Subcommand};
-use tracing::info;
+use ghostnet_indexer::config::{
+    ConfigReloader, IndexedChain, QuarantineSettings, Settings, SettingsLoader,
+};
+use ghostnet_indexer::error::{AppError, InfraError, Result};
+use ghostnet_indexer::handlers::{
+    DeathHandler, EmissionsHandler, FeeHandler, MarketHandler, PositionHandler, ScanHandler,
+    TokenHandler,
+};
+use ghostnet_indexer::indexer::{
+    BlockProcessor, BoostExpirer, CheckpointManager, Contract, ContractRegistry, ContractScope,
+    EventRouter, EventKind, HolderReconciler, Ingest, JournalEntry, LogReplayer, LogRouter,
+    Pipeline, Quarantine, QuarantineRetrier, RecoveryMode, ResumePlan, RetryOutcome,
+    RoundWatcher, Severity, SharedRegistry, StateVerifier, StatsAggregator, TxContextResolver,
+};
+use ghostnet_indexer::obs;
+use ghostnet_indexer::ports::{Cache, HolderStore, QuarantineStore, RawLogStore};
+use ghostnet_indexer::store::{MemoryCache, PostgresStore, RetryPolicy};
+use ghostnet_indexer::streaming::{
+    EventLog, IggyPublisher, OutboxRelay, SequencedPublisher, WireSchemas,
+};
+use ghostnet_indexer::types::primitives::BlockNumber;
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+use tokio::sync::mpsc;
+use tokio::sync::mpsc::error::TrySendError;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info, warn};
+
+/// Capacity of the channel between the block processor and the pipeline.
+const INGEST_CHANNEL_CAPACITY: usize = 10_000;
+
+/// Time allowed past the grace period for the final checkpoint and flush.
+const FINAL_FLUSH_MARGIN: Duration = Duration::from_secs(5);
+
+/// Contracts at most this many blocks behind the others are backfilled
+/// before indexing resumes; contracts further behind catch up alongside.
+const ALIGN_WINDOW_BLOCKS: u64 = 1_000;
 
 /// GHOSTNET Event Indexer
 #[derive(Parser, Debug)]
@@ -24,6 +103,11 @@ struct Cli {
     /// Enable verbose logging
     #[arg(short, long)]
     verbose: bool,
+
+    /// Chain to work on [default: every chain on `run` and `migrate`, the
+    /// primary chain otherwise]
+    #[arg(long, global = true)]
+    chain: Option<u64>,
 }
 
 #[derive(Subcommand, Debug)]
@@ -44,19 +128,85 @@ enum Commands {
 
     /// Backfill historical data
     Backfill {
+        /// Only backfill this contract (e.g. `dead_pool`)
+        #[arg(long)]
+        contract: Option<String>,
+
         /// Starting block number
         #[arg(long)]
         from: u64,
 
-        /// Ending block number
+        /// Ending block number [default: where the other contracts are indexed]
+        #[arg(long)]
+        to: Option<u64>,
+    },
+
+    /// Rebuild aggregate level and global stats from the raw tables
+    RecomputeStats,
+
+    /// Cross-check indexed positions, rounds and stats against the chain
+    Verify {
+        /// Only check this many active positions, picked at random
+        #[arg(long)]
+        sample: Option<usize>,
+
+        /// Overwrite mismatched rows with their on-chain state
+        #[arg(long)]
+        fix: bool,
+
+        /// File the values overwritten by `--fix` are appended to
+        #[arg(long, default_value = "verify-journal.jsonl")]
+        journal: String,
+
+        /// Mismatches tolerated before exiting with status 2
+        #[arg(long, default_value_t = 0)]
+        max_mismatches: usize,
+    },
+
+    /// Run handlers again over the archived raw logs of a block range
+    Replay {
+        /// First block to replay
+        #[arg(long)]
+        from: u64,
+
+        /// Last block to replay [default: the last archived block]
+        #[arg(long)]
+        to: Option<u64>,
+
+        /// Handlers to run, e.g. `scan,market` [default: all]
+        #[arg(long, value_delimiter = ',')]
+        handlers: Vec<String>,
+
+        /// Clear the handlers' tables first; required for position and death
         #[arg(long)]
-        to: u64,
+        truncate_derived: bool,
+    },
+
+    /// List and retry events quarantined by failing handlers
+    Quarantine {
+        #[command(subcommand)]
+        action: QuarantineAction,
     },
 
     /// Show version information
     Version,
 }
 
+#[derive(Subcommand, Debug)]
+enum QuarantineAction {
+    /// List quarantined events in chain order
+    List,
+
+    /// Route a quarantined event through its handler again
+    Retry {
+        /// ID of the event, as listed
+        id: i64,
+    },
+
+    /// Route every quarantined event again, in chain order
+    RetryAll,
+}
+
 fn main() {
     // Parse CLI arguments
     let cli = Cli::parse();
@@ -80,25 +230,1150 @@ fn main() {
     match cli.command {
         Commands::Run { from_block } => {
             info!(?from_block, "Running indexer");
-            // TODO: Implement indexer startup
-            println!("Indexer run command - not yet implemented");
+            let result = block_on(run(&cli.config, cli.chain, from_block));
+            exit_on_error(result, "Indexer failed");
         }
         Commands::Migrate { revert } => {
             if revert {
-                info!("Reverting migrations");
-            } else {
-                info!("Running migrations");
+                error!("Reverting migrations is not supported");
+                std::process::exit(1);
+            }
+            info!("Running migrations");
+            let result = block_on(migrate(&cli.config, cli.chain));
+            exit_on_error(result, "Migration failed");
+        }
+        Commands::Backfill { contract, from, to } => {
+            info!(?contract, from, ?to, "Running backfill");
+            let contract = contract.as_deref();
+            let result = block_on(backfill(&cli.config, cli.chain, contract, from, to));
+            exit_on_error(result, "Backfill failed");
+        }
+        Commands::RecomputeStats => {
+            info!("Recomputing aggregate stats");
+            let result = block_on(recompute_stats(&cli.config, cli.chain));
+            exit_on_error(result, "Stats recomputation failed");
+        }
+        Commands::Verify {
+            sample,
+            fix,
+            journal,
+            max_mismatches,
+        } => {
+            info!(?sample, fix, "Verifying indexed state");
+            let result = block_on(verify(&cli.config, cli.chain, sample, fix, &journal));
+            match result {
+                Ok(mismatches) if mismatches > max_mismatches => {
+                    error!(
+                        mismatches,
+                        max_mismatches, "Indexed state differs from the chain"
+                    );
+                    std::process::exit(2);
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    error!(error = %e, "Verification failed");
+                    std::process::exit(1);
+                }
             }
-            // TODO: Implement migration
-            println!("Migration command - not yet implemented");
         }
-        Commands::Backfill { from, to } => {
-            info!(from, to, "Running backfill");
-            // TODO: Implement backfill
-            println!("Backfill command - not yet implemented");
+        Commands::Replay {
+            from,
+            to,
+            handlers,
+            truncate_derived,
+        } => {
+            info!(from, ?to, ?handlers, truncate_derived, "Replaying raw logs");
+            let result = block_on(replay(
+                &cli.config,
+                cli.chain,
+                from,
+                to,
+                &handlers,
+                truncate_derived,
+            ));
+            exit_on_error(result, "Replay failed");
+        }
+        Commands::Quarantine { action } => {
+            let result = block_on(async {
+                match action {
+                    QuarantineAction::List => list_quarantined(&cli.config, cli.chain).await,
+                    QuarantineAction::Retry { id } => {
+                        retry_quarantined(&cli.config, cli.chain, Some(id)).await
+                    }
+                    QuarantineAction::RetryAll => {
+                        retry_quarantined(&cli.config, cli.chain, None).await
+                    }
+                }
+            });
+            exit_on_error(result, "Quarantine command failed");
         }
         Commands::Version => {
             println!("ghostnet-indexer {}", ghostnet_indexer::VERSION);
         }
     }
 }
+
+/// Run `future` to completion on a runtime of its own.
+fn block_on<T>(future: impl std::future::Future<Output = Result<T>>) -> Result<T> {
+    let runtime = tokio::runtime::Runtime::new()
+        .map_err(|e| InfraError::Internal(format!("Failed to start runtime: {e}")))?;
+    runtime.block_on(future)
+}
+
+/// Log `result`'s error as `failed` and exit with status 1, if it failed.
+fn exit_on_error<T>(result: Result<T>, failed: &str) {
+    if let Err(e) = result {
+        error!(error = %e, "{failed}");
+        std::process::exit(1);
+    }
+}
+
+/// Index new blocks of every chain, or of `chain_id` only, until
+/// SIGINT/SIGTERM, then drain and checkpoint.
+///
+/// Each chain is indexed by a pipeline of its own; one failing stops the
+/// others.
+async fn run(config_path: &str, chain_id: Option<u64>, from_block: Option<u64>) -> Result<()> {
+    let settings = load_settings(config_path)?;
+    settings
+        .validate()
+        .map_err(|errors| AppError::Config(errors.join("; ")))?;
+    let chains = match chain_id {
+        Some(_) => vec![indexed_chain(&settings, chain_id)?],
+        None => settings.indexed_chains(),
+    };
+    if from_block.is_some() && chains.len() > 1 {
+        return Err(AppError::Config(
+            "--from-block needs --chain when several chains are indexed".into(),
+        ));
+    }
+
+    // Cancelled on SIGINT/SIGTERM; a watchdog exits if draining overruns
+    let shutdown = CancellationToken::new();
+    let grace_period = settings.shutdown.grace_period();
+    tokio::spawn({
+        let shutdown = shutdown.clone();
+        async move {
+            wait_for_shutdown_signal().await;
+            shutdown.cancel();
+            tokio::time::sleep(grace_period + FINAL_FLUSH_MARGIN).await;
+            error!(?grace_period, "Shutdown did not complete in time, aborting");
+            std::process::exit(1);
+        }
+    });
+
+    // Metrics are served with the primary chain's store and cache, and only
+    // a single chain's settings can be reloaded
+    let primary = settings.primary_chain_id();
+    let reload = settings.chains.is_empty();
+    let runs = chains.iter().map(|chain| {
+        let serve_metrics = chains.len() == 1 || Some(chain.chain_id) == primary;
+        let settings = settings.for_chain(chain);
+        let shutdown = shutdown.clone();
+        async move {
+            let roles = (serve_metrics, reload);
+            let result = run_chain(
+                config_path,
+                &settings,
+                chain,
+                from_block,
+                roles,
+                shutdown.clone(),
+            )
+            .await;
+            if let Err(e) = &result {
+                error!(chain_id = chain.chain_id, error = %e, "Chain indexing failed");
+                shutdown.cancel();
+            }
+            result
+        }
+    });
+    futures::future::join_all(runs).await.into_iter().collect()
+}
+
+/// Index `chain`, with `settings` of the chain alone, until `shutdown`.
+///
+/// Serves metrics and reloads the config on SIGHUP as `(serve_metrics,
+/// reload)` say.
+async fn run_chain(
+    config_path: &str,
+    settings: &Settings,
+    chain: &IndexedChain,
+    from_block: Option<u64>,
+    (serve_metrics, reload): (bool, bool),
+    shutdown: CancellationToken,
+) -> Result<()> {
+    info!(chain_id = chain.chain_id, schema = %chain.schema, "Indexing chain");
+    let store = connect(settings, chain).await?;
+    let cache = Arc::new(MemoryCache::from_settings(&settings.cache));
+    let reload_cache = Arc::clone(&cache);
+    let metrics_cache: Arc<dyn Cache> = cache.clone();
+    let batch_window = settings.database.batch_window();
+    let writer = writer(&store, batch_window);
+    let quarantine =
+        load_quarantine(&store, &settings.quarantine, chain.chain_id, batch_window).await?;
+    let router = event_router(
+        &writer,
+        &cache,
+        settings.outbox.enabled,
+        (settings.holders.enabled, settings.boosts.enabled),
+        quarantine,
+    );
+
+    let rpc_url = settings
+        .rpc
+        .url
+        .parse()
+        .map_err(|e| AppError::Config(format!("Invalid rpc.url: {e}")))?;
+    let provider = ProviderBuilder::new().connect_http(rpc_url);
+    let contracts = ContractRegistry::from_config(&settings.contracts)?;
+    contracts.verify(&provider).await?;
+    let enabled = enabled_contracts(&contracts);
+    let data_token = contracts.address(Contract::DataToken);
+    let contracts = SharedRegistry::from(contracts);
+
+    let checkpoints =
+        CheckpointManager::new(PostgresStore::clone(&store)).with_chain_id(chain.chain_id);
+    let (checkpoints, plan) = plan_resume(checkpoints, enabled, from_block).await?;
+
+    let metrics_task = (serve_metrics && settings.metrics.enabled).then(|| {
+        let store = Some(PostgresStore::clone(&store));
+        let router = obs::router(obs::install(), Some(metrics_cache), store);
+        let settings = settings.metrics.clone();
+        let shutdown = shutdown.clone();
+        tokio::spawn(async move { obs::serve(&settings, router, shutdown).await })
+    });
+
+    if reload {
+        spawn_config_reloader(
+            config_path,
+            settings,
+            reload_cache,
+            contracts.clone(),
+            provider.clone(),
+            shutdown.clone(),
+        );
+    }
+
+    let publishing = Publishing::spawn(&store, settings)?;
+
+    let reconcile_task =
+        spawn_reconciler(&store, &provider, data_token, settings, shutdown.clone()).await?;
+
+    let scoped = Arc::new(ScopedIndexer::new(
+        provider,
+        contracts.clone(),
+        (Arc::clone(&store), cache),
+        settings,
+        shutdown.clone(),
+    ));
+
+    let start_block = match scoped.align(&plan).await? {
+        Some(head) => head.next(),
+        None => checkpoints.get_start_block().await?,
+    };
+    let catch_up_tasks = scoped.spawn_catch_up(&plan);
+
+    let (ingest_tx, ingest_rx) = mpsc::channel(INGEST_CHANNEL_CAPACITY);
+    let lagging = plan.catch_up.iter().map(|&(contract, _)| contract);
+    let processor = scoped
+        .processor(ingest_tx)
+        .with_scope(ContractScope::Except(lagging.chain(plan.missing).collect()));
+    // The processor owns the only sender; the pipeline drains until it exits
+    let processor_task =
+        tokio::spawn(async move { processor.start_polling(start_block.value()).await });
+
+    let checkpoints = checkpoints.with_store(PostgresStore::clone(&writer));
+    let pipeline = scoped.pipeline(router, checkpoints, &writer);
+    let checkpoint = pipeline.run(ingest_rx, shutdown.clone()).await;
+
+    // Stop the processor too if the pipeline exited on its own
+    shutdown.cancel();
+    join_logged(processor_task, "Block processor").await;
+    for task in catch_up_tasks {
+        join_logged(task, "Catch-up indexing").await;
+    }
+
+    if let Some(task) = reconcile_task
+        && let Err(e) = task.await
+    {
+        warn!(error = %e, "Holder reconcile task panicked");
+    }
+    publishing.shutdown().await;
+
+    if let Some(task) = metrics_task {
+        join_logged(task, "Metrics server").await;
+    }
+
+    let checkpoint = checkpoint?;
+    info!(chain_id = chain.chain_id, ?checkpoint, "Indexer stopped");
+    Ok(())
+}
+
+/// Count the token holders for the metrics and, if holders are reconciled,
+/// spawn the reconciler of `data_token`.
+async fn spawn_reconciler<P: Provider + Clone + 'static>(
+    store: &Arc<PostgresStore>,
+    provider: &P,
+    data_token: Option<Address>,
+    settings: &Settings,
+    shutdown: CancellationToken,
+) -> Result<Option<JoinHandle<()>>> {
+    if settings.holders.enabled {
+        obs::set_token_holders(store.count_holders().await?);
+    }
+    let reconcile_task = data_token
+        .filter(|_| settings.holders.enabled && settings.holders.reconcile)
+        .map(|data_token| {
+            let reconciler = HolderReconciler::new(
+                Arc::clone(store),
+                provider.clone(),
+                data_token,
+                &settings.holders,
+            );
+            Arc::new(reconciler).spawn_reconcile_task(shutdown)
+        });
+    Ok(reconcile_task)
+}
+
+/// Wait for the `what` task, logging whether it failed or panicked.
+async fn join_logged<T, E: std::fmt::Display>(
+    task: JoinHandle<std::result::Result<T, E>>,
+    what: &str,
+) {
+    match task.await {
+        Ok(Ok(_)) => {}
+        Ok(Err(e)) => error!(error = %e, "{what} failed"),
+        Err(e) => error!(error = %e, "{what} task panicked"),
+    }
+}
+
+/// Decide where each enabled contract resumes, see [`ResumePlan`].
+///
+/// Starting from a given block resumes every enabled contract from there.
+async fn plan_resume(
+    checkpoints: CheckpointManager<PostgresStore>,
+    enabled: Vec<Contract>,
+    from_block: Option<u64>,
+) -> Result<(CheckpointManager<PostgresStore>, ResumePlan)> {
+    let checkpoints = checkpoints.with_enabled(enabled.clone());
+    let (checkpoints, plan) = if let Some(block) = from_block {
+        let mode = RecoveryMode::StartFrom(BlockNumber::new(block));
+        let plan = ResumePlan {
+            contracts: enabled,
+            ..ResumePlan::default()
+        };
+        (checkpoints.with_recovery_mode(mode), plan)
+    } else {
+        let plan = checkpoints
+            .resume_plan(&enabled, ALIGN_WINDOW_BLOCKS)
+            .await?;
+        (checkpoints, plan)
+    };
+
+    for contract in &plan.missing {
+        warn!(%contract, "Contract has never been indexed, skipping until backfilled");
+    }
+    Ok((checkpoints.with_contracts(plan.contracts.clone()), plan))
+}
+
+/// Backfill one or all enabled contracts, moving only their cursors.
+///
+/// Without `to`, backfills up to where the other contracts are indexed, so
+/// the next `run` resumes the contract with them.
+async fn backfill(
+    config_path: &str,
+    chain_id: Option<u64>,
+    contract: Option<&str>,
+    from: u64,
+    to: Option<u64>,
+) -> Result<()> {
+    let (settings, chain) = load_chain(config_path, chain_id)?;
+    let store = connect(&settings, &chain).await?;
+    let rpc_url = settings
+        .rpc
+        .url
+        .parse()
+        .map_err(|e| AppError::Config(format!("Invalid rpc.url: {e}")))?;
+    let provider = ProviderBuilder::new().connect_http(rpc_url);
+    let registry = ContractRegistry::from_config(&settings.contracts)?;
+    let enabled = enabled_contracts(&registry);
+
+    let (scope, tracked) = match contract {
+        Some(name) => {
+            let contract = name.parse::<Contract>().map_err(AppError::Config)?;
+            if !registry.is_enabled(contract) {
+                return Err(AppError::Config(format!("Contract {contract} is disabled")));
+            }
+            (ContractScope::Only(contract), vec![contract])
+        }
+        None => (ContractScope::All, enabled.clone()),
+    };
+
+    let checkpoints =
+        CheckpointManager::new(PostgresStore::clone(&store)).with_chain_id(chain.chain_id);
+    let to = match to {
+        Some(to) => BlockNumber::new(to),
+        None => checkpoints
+            .resume_plan(&enabled, 0)
+            .await?
+            .head
+            .ok_or_else(|| AppError::Config("Nothing indexed yet, pass --to".into()))?,
+    };
+    if from > to.value() {
+        return Err(AppError::Config(format!("--from {from} is past --to {to}")));
+    }
+    for (contract, cursor) in checkpoints.cursors(&tracked).await? {
+        let Some(cursor) = cursor else { continue };
+        if from > cursor.next().value() {
+            return Err(AppError::Config(format!(
+                "{contract} is indexed through {cursor}, backfilling from {from} would skip blocks"
+            )));
+        }
+        if to < cursor {
+            return Err(AppError::Config(format!(
+                "{contract} is already indexed through {cursor}, past --to {to}"
+            )));
+        }
+        if from <= cursor.value() {
+            warn!(%contract, %cursor, from, "Backfill overlaps blocks already indexed");
+        }
+    }
+
+    let shutdown = CancellationToken::new();
+    tokio::spawn({
+        let shutdown = shutdown.clone();
+        async move {
+            wait_for_shutdown_signal().await;
+            shutdown.cancel();
+        }
+    });
+
+    let contracts = SharedRegistry::from(registry);
+    let cache = Arc::new(MemoryCache::from_settings(&settings.cache));
+    let scoped = ScopedIndexer::new(provider, contracts, (store, cache), &settings, shutdown);
+    let checkpoint = scoped.run(scope, tracked, from, Some(to)).await?;
+    info!(?checkpoint, "Backfill finished");
+    Ok(())
+}
+
+/// Rebuild level and global stats from the database, correcting any drift.
+async fn recompute_stats(config_path: &str, chain_id: Option<u64>) -> Result<()> {
+    let (settings, chain) = load_chain(config_path, chain_id)?;
+    let store = connect(&settings, &chain).await?;
+    let aggregator = StatsAggregator::new(store, Arc::new(MemoryCache::new()), &settings.stats);
+    let levels = aggregator.recompute_from_db().await?;
+
+    for stats in &levels {
+        println!(
+            "{:<10} staked={} effective={} alive={} deaths={} (24h: {}) extracted={} avg_streak={:.2}",
+            stats.level.name(),
+            stats.total_staked,
+            stats.total_effective_staked,
+            stats.alive_count,
+            stats.total_deaths,
+            stats.deaths_24h,
+            stats.total_extracted,
+            stats.average_ghost_streak(),
+        );
+    }
+
+    Ok(())
+}
+
+/// Compare indexed state with the chain, and with `fix` overwrite what
+/// differs, journaling each change to `journal`.
+///
+/// Returns the number of mismatches found.
+async fn verify(
+    config_path: &str,
+    chain_id: Option<u64>,
+    sample: Option<usize>,
+    fix: bool,
+    journal: &str,
+) -> Result<usize> {
+    let (settings, chain) = load_chain(config_path, chain_id)?;
+    let store = connect(&settings, &chain).await?;
+    let rpc_url = settings
+        .rpc
+        .url
+        .parse()
+        .map_err(|e| AppError::Config(format!("Invalid rpc.url: {e}")))?;
+    let provider = ProviderBuilder::new().connect_http(rpc_url);
+    let registry = ContractRegistry::from_config(&settings.contracts)?;
+    let ghost_core = registry
+        .address(Contract::GhostCore)
+        .ok_or_else(|| AppError::Config("ghost_core is disabled, nothing to verify".into()))?;
+    let mut verifier = StateVerifier::new(store, provider, ghost_core);
+    if let Some(dead_pool) = registry.address(Contract::DeadPool) {
+        verifier = verifier.with_dead_pool(dead_pool);
+    }
+
+    let report = verifier.verify(sample).await?;
+    println!(
+        "Verified at block {}: {} positions, {} rounds",
+        report.block, report.positions_checked, report.rounds_checked
+    );
+    for skipped in &report.skipped {
+        println!("{:<9} {skipped}", "skipped");
+    }
+    for mismatch in report.mismatches() {
+        println!(
+            "{:<9} {} {}: indexed={} chain={}",
+            mismatch.severity,
+            mismatch.subject,
+            mismatch.field,
+            mismatch.indexed,
+            mismatch.on_chain
+        );
+    }
+    println!(
+        "{} mismatches, {} critical",
+        report.mismatches().len(),
+        report.count_at_least(Severity::Critical)
+    );
+
+    if fix && !report.is_consistent() {
+        let entries = verifier.fix(&report).await?;
+        append_journal(journal, &entries)?;
+        println!("Fixed {} values, journaled to {journal}", entries.len());
+    }
+    Ok(report.mismatches().len())
+}
+
+/// Route the archived logs of `from` through `to` through `handlers` again,
+/// recomputing the aggregate stats if positions were rebuilt.
+async fn replay(
+    config_path: &str,
+    chain_id: Option<u64>,
+    from: u64,
+    to: Option<u64>,
+    handlers: &[String],
+    truncate_derived: bool,
+) -> Result<()> {
+    let (settings, chain) = load_chain(config_path, chain_id)?;
+    let store = connect(&settings, &chain).await?;
+    let to = match to {
+        Some(to) => BlockNumber::new(to),
+        None => store
+            .raw_log_range()
+            .await?
+            .map(|(_, last)| last)
+            .ok_or_else(|| AppError::Config("No raw logs archived, nothing to replay".into()))?,
+    };
+    if from > to.value() {
+        return Err(AppError::Config(format!("--from {from} is past --to {to}")));
+    }
+    let mut handlers: Vec<&str> = handlers.iter().map(String::as_str).collect();
+    if handlers.is_empty() {
+        handlers = EventKind::ALL.into_iter().map(EventKind::handler).collect();
+    }
+
+    let shutdown = CancellationToken::new();
+    tokio::spawn({
+        let shutdown = shutdown.clone();
+        async move {
+            wait_for_shutdown_signal().await;
+            shutdown.cancel();
+        }
+    });
+
+    // Batched, so each block commits with the replay's progress
+    let batched = Arc::new(store.batched());
+    let cache = Arc::new(MemoryCache::from_settings(&settings.cache));
+    let tracked = (settings.holders.enabled, settings.boosts.enabled);
+    let router = event_router(&batched, &cache, false, tracked, None);
+    let replayer = LogReplayer::new(batched, router, &handlers)?
+        .with_truncate_derived(truncate_derived)
+        .with_shutdown(shutdown);
+    let report = replayer.replay(BlockNumber::new(from), to).await?;
+    println!(
+        "Replayed {} logs ({} skipped, {} failed) through block {}",
+        report.routed,
+        report.skipped,
+        report.failed,
+        report.last_block.map_or_else(|| "none".into(), |block| block.to_string())
+    );
+    if !report.complete {
+        println!("Stopped before the end; run the same command again to resume");
+        return Ok(());
+    }
+
+    if replayer.handlers().contains(&"position") {
+        let aggregator = StatsAggregator::new(store, cache, &settings.stats);
+        aggregator.recompute_from_db().await?;
+        println!("Recomputed aggregate stats");
+    }
+    Ok(())
+}
+
+/// Print the quarantined events in chain order.
+async fn list_quarantined(config_path: &str, chain_id: Option<u64>) -> Result<()> {
+    let (settings, chain) = load_chain(config_path, chain_id)?;
+    let store = connect(&settings, &chain).await?;
+    let events = store.list_quarantined().await?;
+
+    for event in &events {
+        let log = &event.log;
+        let kind = log
+            .topics
+            .first()
+            .and_then(EventKind::from_topic)
+            .map_or_else(|| "unknown".to_string(), |kind| format!("{kind:?}"));
+        let blocked_by = event
+            .blocked_by
+            .map_or_else(String::new, |id| format!(" blocked_by=#{id}"));
+        println!(
+            "#{:<6} block={} tx={} log={} {}:{kind} attempts={}{blocked_by} {}",
+            event.id,
+            log.block_number,
+            log.tx_hash,
+            log.log_index,
+            event.handler,
+            event.attempts,
+            event.error,
+        );
+    }
+    println!("{} quarantined events", events.len());
+    Ok(())
+}
+
+/// Route the quarantined event `id` through its handler again, or every
+/// quarantined event without one, releasing those that go through.
+async fn retry_quarantined(
+    config_path: &str,
+    chain_id: Option<u64>,
+    id: Option<i64>,
+) -> Result<()> {
+    let (settings, chain) = load_chain(config_path, chain_id)?;
+    let store = connect(&settings, &chain).await?;
+
+    // Batched, so an event that fails again leaves nothing behind
+    let batched = Arc::new(store.batched());
+    let cache = Arc::new(MemoryCache::from_settings(&settings.cache));
+    let tracked = (settings.holders.enabled, settings.boosts.enabled);
+    let router = event_router(&batched, &cache, settings.outbox.enabled, tracked, None);
+    let retrier = QuarantineRetrier::new(batched, router);
+
+    let Some(id) = id else {
+        let report = retrier.retry_all().await?;
+        println!(
+            "Released {} quarantined events, {} blocked, {} failed again",
+            report.released, report.blocked, report.failed
+        );
+        return Ok(());
+    };
+    match retrier.retry(id).await? {
+        RetryOutcome::Released => println!("Released quarantined event #{id}"),
+        RetryOutcome::Blocked(blocker) => {
+            println!("Quarantined event #{id} is blocked by #{blocker}, retry that first");
+        }
+        RetryOutcome::Failed(error) => println!("Quarantined event #{id} failed again: {error}"),
+    }
+    Ok(())
+}
+
+/// Create the schema of every chain, or of `chain_id` only, and run pending
+/// migrations in it.
+async fn migrate(config_path: &str, chain_id: Option<u64>) -> Result<()> {
+    let settings = load_settings(config_path)?;
+    let chains = match chain_id {
+        Some(_) => vec![indexed_chain(&settings, chain_id)?],
+        None => settings.indexed_chains(),
+    };
+    for chain in &chains {
+        let store = connect(&settings, chain).await?;
+        store.run_schema_migrations(&chain.schema).await?;
+        info!(chain_id = chain.chain_id, schema = %chain.schema, "Migrated chain");
+    }
+    Ok(())
+}
+
+/// Append `entries` to the file at `path`, one JSON object per line.
+fn append_journal(path: &str, entries: &[JournalEntry]) -> Result<()> {
+    let io_error = |e: std::io::Error| InfraError::Internal(format!("Failed to write {path}: {e}"));
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(io_error)?;
+    for entry in entries {
+        let line = serde_json::to_string(entry).map_err(InfraError::Serialization)?;
+        writeln!(file, "{line}").map_err(io_error)?;
+    }
+    Ok(())
+}
+
+/// Background tasks publishing events to Iggy.
+struct Publishing {
+    publisher_task: JoinHandle<()>,
+    publisher_shutdown: CancellationToken,
+    relay_task: Option<JoinHandle<()>>,
+    watcher_task: Option<JoinHandle<()>>,
+    expiry_task: Option<JoinHandle<()>>,
+    purge_task: JoinHandle<()>,
+    relay_shutdown: CancellationToken,
+}
+
+impl Publishing {
+    /// Start the publisher's flush task, the event log's purge task, and
+    /// the outbox relay, round watcher and boost expirer if enabled.
+    ///
+    /// The relay, the watcher and the expirer publish through the event log,
+    /// which assigns the sequences the WebSocket API delivers.
+    fn spawn(store: &Arc<PostgresStore>, settings: &Settings) -> Result<Self> {
+        let schemas = Arc::new(WireSchemas::new(&settings.schemas));
+        let iggy = Arc::new(IggyPublisher::new(&settings.iggy)?.with_schemas(Arc::clone(&schemas)));
+        let publisher_shutdown = CancellationToken::new();
+        let publisher_task = iggy.spawn_flush_task(publisher_shutdown.clone());
+
+        let relay_shutdown = CancellationToken::new();
+        let event_log = Arc::new(EventLog::new(store.clone(), &settings.api.websocket));
+        let purge_task = event_log.spawn_purge_task(relay_shutdown.clone());
+        let publisher = Arc::new(SequencedPublisher::new(iggy, event_log));
+        let relay_task = settings.outbox.enabled.then(|| {
+            let relay =
+                OutboxRelay::new(Arc::clone(store), Arc::clone(&publisher), &settings.outbox)
+                    .with_schemas(Arc::clone(&schemas))
+                    .with_chain_id(settings.rpc.chain_id);
+            Arc::new(relay).spawn_relay_task(relay_shutdown.clone())
+        });
+        let watcher_task = settings.round_watcher.enabled.then(|| {
+            let publisher = Arc::clone(&publisher);
+            let watcher = RoundWatcher::new(Arc::clone(store), publisher, &settings.round_watcher)
+                .with_schemas(Arc::clone(&schemas));
+            Arc::new(watcher).spawn_watch_task(relay_shutdown.clone())
+        });
+        let expiry_task = settings.boosts.enabled.then(|| {
+            let expirer = BoostExpirer::new(Arc::clone(store), publisher, &settings.boosts)
+                .with_schemas(schemas);
+            Arc::new(expirer).spawn_expiry_task(relay_shutdown.clone())
+        });
+
+        Ok(Self {
+            publisher_task,
+            publisher_shutdown,
+            relay_task,
+            watcher_task,
+            expiry_task,
+            purge_task,
+            relay_shutdown,
+        })
+    }
+
+    /// Stop the relay, the round watcher and the boost expirer, then flush
+    /// and stop the publisher.
+    ///
+    /// Call once indexing stopped; the relay publishes what the drain
+    /// committed before the publisher closes.
+    async fn shutdown(self) {
+        self.relay_shutdown.cancel();
+        if let Some(task) = self.relay_task
+            && let Err(e) = task.await
+        {
+            warn!(error = %e, "Outbox relay task panicked");
+        }
+        if let Some(task) = self.watcher_task
+            && let Err(e) = task.await
+        {
+            warn!(error = %e, "Round watcher task panicked");
+        }
+        if let Some(task) = self.expiry_task
+            && let Err(e) = task.await
+        {
+            warn!(error = %e, "Boost expiry task panicked");
+        }
+        if let Err(e) = self.purge_task.await {
+            warn!(error = %e, "Event log purge task panicked");
+        }
+
+        self.publisher_shutdown.cancel();
+        if let Err(e) = self.publisher_task.await {
+            warn!(error = %e, "Publisher flush task panicked");
+        }
+    }
+}
+
+/// Builds processors and indexes contracts on their own, moving only their
+/// cursors.
+struct ScopedIndexer<P> {
+    chain_id: u64,
+    provider: Arc<P>,
+    contracts: SharedRegistry,
+    store: Arc<PostgresStore>,
+    cache: Arc<MemoryCache>,
+    outbox: bool,
+    /// Whether holders and boosts are tracked
+    tracked: (bool, bool),
+    raw_logs: bool,
+    quarantine: QuarantineSettings,
+    tx_context: Option<Arc<TxContextResolver>>,
+    poll_interval: Duration,
+    grace_period: Duration,
+    batch_window: Option<Duration>,
+    shutdown: CancellationToken,
+}
+
+impl<P: Provider + Clone + 'static> ScopedIndexer<P> {
+    fn new(
+        provider: P,
+        contracts: SharedRegistry,
+        (store, cache): (Arc<PostgresStore>, Arc<MemoryCache>),
+        settings: &Settings,
+        shutdown: CancellationToken,
+    ) -> Self {
+        let tx_context = TxContextResolver::from_settings(provider.clone(), &settings.tx_context);
+        if tx_context.is_some() {
+            info!("Transaction context enabled");
+        }
+        Self {
+            chain_id: settings.rpc.chain_id,
+            provider: Arc::new(provider),
+            contracts,
+            store,
+            cache,
+            outbox: settings.outbox.enabled,
+            tracked: (settings.holders.enabled, settings.boosts.enabled),
+            raw_logs: settings.raw_logs.enabled,
+            quarantine: settings.quarantine.clone(),
+            tx_context: tx_context.map(Arc::new),
+            poll_interval: settings.rpc.poll_interval(),
+            grace_period: settings.shutdown.grace_period(),
+            batch_window: settings.database.batch_window(),
+            shutdown,
+        }
+    }
+
+    /// A processor of every enabled contract, sending to `ingest`.
+    fn processor(&self, ingest: mpsc::Sender<Ingest>) -> BlockProcessor<P> {
+        let processor = BlockProcessor::new(
+            Arc::clone(&self.provider),
+            self.contracts.clone(),
+            ingest,
+            Some(self.poll_interval),
+        )
+        .with_chain_id(self.chain_id)
+        .with_shutdown(self.shutdown.clone());
+        match &self.tx_context {
+            Some(resolver) => processor.with_tx_context(Arc::clone(resolver)),
+            None => processor,
+        }
+    }
+
+    /// A pipeline routing through `router`, archiving raw logs in `store`
+    /// if `raw_logs.enabled` is set.
+    fn pipeline<R: LogRouter>(
+        &self,
+        router: R,
+        checkpoints: CheckpointManager<PostgresStore>,
+        store: &Arc<PostgresStore>,
+    ) -> Pipeline<R, PostgresStore> {
+        Pipeline::new(router, checkpoints)
+            .with_grace_period(self.grace_period)
+            .with_batch_window(self.batch_window)
+            .with_archive(
+                self.raw_logs
+                    .then(|| Arc::clone(store) as Arc<dyn RawLogStore>),
+            )
+    }
+
+    /// Index `scope` from `from` through `to`, or until shutdown without
+    /// `to`, checkpointing the cursors of `tracked`.
+    async fn run(
+        &self,
+        scope: ContractScope,
+        tracked: Vec<Contract>,
+        from: u64,
+        to: Option<BlockNumber>,
+    ) -> Result<Option<BlockNumber>> {
+        let (ingest_tx, ingest_rx) = mpsc::channel(INGEST_CHANNEL_CAPACITY);
+        let processor = self.processor(ingest_tx).with_scope(scope);
+        let processor_task = tokio::spawn(async move {
+            match to {
+                Some(to) => processor.backfill(from, to.value()).await,
+                None => processor.start_polling(from).await,
+            }
+        });
+
+        // Each run batches on its own, so concurrent runs never share a batch
+        let store = writer(&self.store, self.batch_window);
+        let checkpoints = CheckpointManager::for_contracts(PostgresStore::clone(&store), tracked)
+            .with_chain_id(self.chain_id);
+        let quarantine = load_quarantine(
+            &self.store,
+            &self.quarantine,
+            self.chain_id,
+            self.batch_window,
+        )
+        .await?;
+        let router = event_router(
+            &store,
+            &self.cache,
+            self.outbox,
+            self.tracked,
+            quarantine,
+        );
+        let pipeline = self.pipeline(router, checkpoints, &store);
+        let checkpoint = pipeline.run(ingest_rx, self.shutdown.clone()).await;
+
+        processor_task
+            .await
+            .map_err(|e| InfraError::Internal(format!("Block processor task panicked: {e}")))??;
+        checkpoint
+    }
+
+    /// Backfill the contracts slightly behind up to the head of `plan`.
+    ///
+    /// Returns the head, after which all of the plan's contracts resume.
+    async fn align(&self, plan: &ResumePlan) -> Result<Option<BlockNumber>> {
+        let Some(head) = plan.head else {
+            return Ok(None);
+        };
+        for &(contract, from) in &plan.align {
+            info!(%contract, from = from.value(), to = head.value(), "Aligning contract");
+            self.run(
+                ContractScope::Only(contract),
+                vec![contract],
+                from.value(),
+                Some(head),
+            )
+            .await?;
+        }
+        Ok(Some(head))
+    }
+
+    /// Index each contract too far behind in `plan` on its own until shutdown.
+    fn spawn_catch_up(
+        self: &Arc<Self>,
+        plan: &ResumePlan,
+    ) -> Vec<JoinHandle<Result<Option<BlockNumber>>>>
+    where
+        P: Send + Sync,
+    {
+        plan.catch_up
+            .iter()
+            .map(|&(contract, from)| {
+                info!(%contract, from = from.value(), "Contract is behind, catching up on its own");
+                let scoped = Arc::clone(self);
+                tokio::spawn(async move {
+                    scoped
+                        .run(
+                            ContractScope::Only(contract),
+                            vec![contract],
+                            from.value(),
+                            None,
+                        )
+                        .await
+                })
+            })
+            .collect()
+    }
+}
+
+/// Store for a pipeline's writes: a batched one on the pool of `store` with a
+/// `batch_window`, `store` itself otherwise.
+fn writer(store: &Arc<PostgresStore>, batch_window: Option<Duration>) -> Arc<PostgresStore> {
+    match batch_window {
+        Some(_) => Arc::new(store.batched()),
+        None => Arc::clone(store),
+    }
+}
+
+/// Quarantine of the logs whose handler keeps failing, with those
+/// quarantined before loaded, if `settings.enabled` is set.
+///
+/// Transient errors are not retried with a `batch_window`: the pipeline
+/// routes the batch again instead.
+async fn load_quarantine(
+    store: &Arc<PostgresStore>,
+    settings: &QuarantineSettings,
+    chain_id: u64,
+    batch_window: Option<Duration>,
+) -> Result<Option<Quarantine>> {
+    if !settings.enabled {
+        return Ok(None);
+    }
+    let quarantine = Quarantine::new(Arc::clone(store) as Arc<dyn QuarantineStore>, settings)
+        .with_chain_id(chain_id);
+    let quarantine = match batch_window {
+        Some(_) => quarantine.with_retry(RetryPolicy::NONE),
+        None => quarantine,
+    };
+    let depth = quarantine.load().await?;
+    info!(chain_id, depth, "Quarantine enabled");
+    Ok(Some(quarantine))
+}
+
+/// Route events to handlers backed by `store` and `cache`, writing position
+/// events to the outbox if `outbox` is set, tracking holder balances and
+/// boosts as `(holders, boosts)` are, and quarantining logs whose handler
+/// fails if given a `quarantine`.
+fn event_router(
+    store: &Arc<PostgresStore>,
+    cache: &Arc<MemoryCache>,
+    outbox: bool,
+    (holders, boosts): (bool, bool),
+    quarantine: Option<Quarantine>,
+) -> impl LogRouter + use<> {
+    let position_handler = PositionHandler::new(store.clone(), cache.clone());
+    let position_handler = if outbox {
+        position_handler.with_outbox()
+    } else {
+        position_handler
+    };
+    let position_handler = if boosts {
+        position_handler.with_boosts(store.clone())
+    } else {
+        position_handler
+    };
+    let token_handler = TokenHandler::new(cache.clone());
+    let token_handler = if holders {
+        token_handler.with_holder_balances(store.clone())
+    } else {
+        token_handler
+    };
+    EventRouter::new(
+        position_handler,
+        ScanHandler::new(store.clone(), cache.clone()),
+        DeathHandler::new(store.clone(), store.clone(), cache.clone()),
+        MarketHandler::new(store.clone(), cache.clone()),
+        token_handler,
+        FeeHandler::new(cache.clone()),
+        EmissionsHandler::new(cache.clone()),
+    )
+    .with_quarantine(quarantine)
+}
+
+/// Enabled contracts, once each.
+fn enabled_contracts(registry: &ContractRegistry) -> Vec<Contract> {
+    let mut enabled: Vec<_> = registry.contracts().collect();
+    enabled.dedup();
+    enabled
+}
+
+/// Load the settings of chain `chain_id` alone, or of the primary chain
+/// without one, see [`Settings::for_chain`].
+fn load_chain(config_path: &str, chain_id: Option<u64>) -> Result<(Settings, IndexedChain)> {
+    let settings = load_settings(config_path)?;
+    let chain = indexed_chain(&settings, chain_id)?;
+    Ok((settings.for_chain(&chain), chain))
+}
+
+/// The indexed chain `chain_id`, or the primary chain without one.
+fn indexed_chain(settings: &Settings, chain_id: Option<u64>) -> Result<IndexedChain> {
+    settings.indexed_chain(chain_id).ok_or_else(|| {
+        let message = chain_id.map_or_else(
+            || "No chain is indexed".to_string(),
+            |chain_id| format!("Chain {chain_id} is not indexed"),
+        );
+        AppError::Config(message)
+    })
+}
+
+/// Load settings for the environment named by the config file.
+fn load_settings(config_path: &str) -> Result<Settings> {
+    // `config/<env>.toml` is layered over the built-in defaults and `config/default.toml`
+    let environment = std::path::Path::new(config_path)
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("default");
+    Ok(Settings::load(environment).map_err(InfraError::Config)?)
+}
+
+/// Connect to the database, scoped to the schema of `chain`, with the
+/// configured statement timeout, pool bounds and connection retries.
+async fn connect(settings: &Settings, chain: &IndexedChain) -> Result<Arc<PostgresStore>> {
+    let database = &settings.database;
+    let options = PgConnectOptions::from_str(&database.url)
+        .map_err(InfraError::Database)?
+        .application_name(&database.application_name)
+        .options([(
+            "statement_timeout",
+            database.statement_timeout().as_millis().to_string(),
+        )]);
+    let options = PostgresStore::chain_options(options, &chain.schema, chain.chain_id);
+    let pool = PgPoolOptions::new()
+        .max_connections(database.max_connections)
+        .min_connections(database.min_connections)
+        .acquire_timeout(database.connect_timeout())
+        .idle_timeout(database.idle_timeout())
+        .connect_with(options)
+        .await
+        .map_err(InfraError::Database)?;
+
+    let store = PostgresStore::new(pool).with_retry_policy(database.retry_policy());
+    Ok(Arc::new(store))
+}
+
+/// Reload the runtime-changeable settings on SIGHUP until shutdown.
+fn spawn_config_reloader<P: Provider + 'static>(
+    config_path: &str,
+    settings: &Settings,
+    cache: Arc<MemoryCache>,
+    contracts: SharedRegistry,
+    provider: P,
+    shutdown: CancellationToken,
+) {
+    let config_path = config_path.to_string();
+    let loader: SettingsLoader = Box::new(move || load_settings(&config_path));
+    let reloader = ConfigReloader::new(settings.clone(), loader, cache, contracts, provider);
+
+    // One pending request covers any signals received while reloading
+    let (requests, receiver) = mpsc::channel(1);
+    #[cfg(unix)]
+    tokio::spawn(forward_reload_signals(requests));
+    #[cfg(not(unix))]
+    drop(requests);
+    tokio::spawn(reloader.run(receiver, shutdown));
+}
+
+/// Request a config reload on every SIGHUP.
+#[cfg(unix)]
+async fn forward_reload_signals(requests: mpsc::Sender<()>) {
+    let mut hangup =
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::hangup()) {
+            Ok(signal) => signal,
+            Err(e) => {
+                error!(error = %e, "Failed to install SIGHUP handler");
+                return;
+            }
+        };
+
+    while hangup.recv().await.is_some() {
+        info!("Received SIGHUP, reloading configuration");
+        if matches!(requests.try_send(()), Err(TrySendError::Closed(()))) {
+            return;
+        }
+    }
+}
+
+/// Wait for shutdown signal (SIGINT or SIGTERM).
+async fn wait_for_shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            error!(error = %e, "Failed to install Ctrl+C handler");
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(e) => error!(error = %e, "Failed to install SIGTERM handler"),
+        }
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        () = ctrl_c => {
+            warn!("Received Ctrl+C, initiating graceful shutdown...");
+        }
+        () = terminate => {
+            warn!("Received SIGTERM, initiating graceful shutdown...");
+        }
+    }
+}
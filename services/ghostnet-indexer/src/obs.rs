@@ -0,0 +1,490 @@
+//! Metrics facade and Prometheus endpoint.
+//!
+//! Components record metrics through the functions in this module, e.g.
+//! `obs::record_store_latency("save_round", elapsed)`, instead of calling the
+//! `metrics` macros themselves. Metric names and labels are defined here
+//! once, and nothing else depends on the backend. Until [`install`] sets up
+//! the Prometheus recorder every call is a no-op.
+//!
+//! # Metrics
+//!
+//! | Metric | Type | Labels | Description |
+//! |--------|------|--------|-------------|
+//! | `indexer_logs_received_total` | counter | `source` | Logs received from RPC (`http`, `ws`) |
+//! | `indexer_events_decoded_total` | counter | `contract` | Logs decoded as a known event |
+//! | `indexer_handler_results_total` | counter | `handler`, `outcome` | Handler calls (`success`, `failure`) |
+//! | `indexer_store_latency_seconds` | histogram | `method` | Store operation latency |
+//! | `indexer_cache_hits_total` | counter | | Cache hits (from [`CacheStats`]) |
+//! | `indexer_cache_misses_total` | counter | | Cache misses (from [`CacheStats`]) |
+//! | `indexer_iggy_published_total` | counter | `topic` | Messages delivered to Iggy |
+//! | `indexer_iggy_dead_lettered_total` | counter | `topic` | Messages sent to the dead-letter sink |
+//! | `indexer_outbox_depth` | gauge | `chain` | Events in the outbox not yet published |
+//! | `indexer_outbox_publish_failures_total` | counter | `topic` | Outbox events whose publish failed (retried later) |
+//! | `indexer_lag_blocks` | gauge | `chain` | Blocks between the chain head and the last indexed block |
+//! | `indexer_cursor_lag_blocks` | gauge | `chain` | Blocks the slowest contract cursor trails the last indexed block |
+//! | `indexer_config_reloads_total` | counter | `outcome` | Config reloads (`success`, `failure`) |
+//! | `indexer_store_retries_total` | counter | `operation` | Store operations retried after a connection error |
+//! | `indexer_db_pool_connections` | gauge | `state` | Pooled connections (`in_use`, `idle`) |
+//! | `indexer_db_pool_utilization` | gauge | | Share of the pool's capacity in use, from 0 to 1 |
+//! | `indexer_store_degraded` | gauge | | 1 while the pipeline is paused on an unavailable store |
+//! | `indexer_store_outage_seconds` | histogram | | How long each pause on an unavailable store lasted |
+//! | `indexer_token_holders` | gauge | | Addresses holding DATA |
+//! | `indexer_holder_reconciliations_total` | counter | `outcome` | Holder balances compared with `balanceOf` (`match`, `corrected`, `skipped`) |
+//! | `indexer_holder_balance_drift_tokens` | histogram | | DATA by which a corrected holder balance was off |
+//! | `indexer_events_quarantined_total` | counter | `handler` | Logs quarantined after their handler failed on them |
+//! | `indexer_quarantine_depth` | gauge | `chain` | Logs in quarantine, waiting for a retry |
+//!
+//! # Endpoint
+//!
+//! [`serve`] exposes the metrics at `/metrics` on the address from
+//! [`MetricsSettings`]. Cache counters are read from the cache, and pool
+//! usage from the store, on each scrape.
+
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+
+use axum::Router;
+use axum::extract::State;
+use axum::routing::get;
+use metrics_exporter_prometheus::{Matcher, PrometheusBuilder, PrometheusHandle};
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use crate::config::MetricsSettings;
+use crate::error::{InfraError, Result};
+use crate::indexer::Contract;
+use crate::ports::{Cache, CacheStats};
+use crate::store::{PoolHealth, PostgresStore};
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// METRIC NAMES
+// ═══════════════════════════════════════════════════════════════════════════════
+
+const LOGS_RECEIVED: &str = "indexer_logs_received_total";
+const EVENTS_DECODED: &str = "indexer_events_decoded_total";
+const HANDLER_RESULTS: &str = "indexer_handler_results_total";
+const STORE_LATENCY: &str = "indexer_store_latency_seconds";
+const CACHE_HITS: &str = "indexer_cache_hits_total";
+const CACHE_MISSES: &str = "indexer_cache_misses_total";
+const IGGY_PUBLISHED: &str = "indexer_iggy_published_total";
+const IGGY_DEAD_LETTERED: &str = "indexer_iggy_dead_lettered_total";
+const OUTBOX_DEPTH: &str = "indexer_outbox_depth";
+const OUTBOX_PUBLISH_FAILURES: &str = "indexer_outbox_publish_failures_total";
+const LAG_BLOCKS: &str = "indexer_lag_blocks";
+const CURSOR_LAG_BLOCKS: &str = "indexer_cursor_lag_blocks";
+const CONFIG_RELOADS: &str = "indexer_config_reloads_total";
+const STORE_RETRIES: &str = "indexer_store_retries_total";
+const POOL_CONNECTIONS: &str = "indexer_db_pool_connections";
+const POOL_UTILIZATION: &str = "indexer_db_pool_utilization";
+const STORE_DEGRADED: &str = "indexer_store_degraded";
+const STORE_OUTAGE: &str = "indexer_store_outage_seconds";
+const TOKEN_HOLDERS: &str = "indexer_token_holders";
+const HOLDER_RECONCILIATIONS: &str = "indexer_holder_reconciliations_total";
+const EVENTS_QUARANTINED: &str = "indexer_events_quarantined_total";
+const QUARANTINE_DEPTH: &str = "indexer_quarantine_depth";
+const HOLDER_DRIFT: &str = "indexer_holder_balance_drift_tokens";
+
+/// Histogram buckets for store latency, in seconds (1ms to 5s).
+const STORE_LATENCY_BUCKETS: [f64; 10] =
+    [0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 1.0, 5.0];
+
+/// Histogram buckets for holder balance drift, in DATA (1 gwei to 1M DATA).
+const HOLDER_DRIFT_BUCKETS: [f64; 9] = [1e-9, 1e-3, 1.0, 10.0, 100.0, 1e3, 1e4, 1e5, 1e6];
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// RECORDING
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Where a log was received from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogSource {
+    /// HTTP polling (`eth_getLogs`).
+    Http,
+    /// WebSocket subscription.
+    Ws,
+}
+
+impl LogSource {
+    /// Label value of the source.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Http => "http",
+            Self::Ws => "ws",
+        }
+    }
+}
+
+/// Count logs received from RPC.
+pub fn record_logs_received(source: LogSource, count: usize) {
+    metrics::counter!(LOGS_RECEIVED, "source" => source.as_str()).increment(count as u64);
+}
+
+/// Count a log decoded as an event of `contract`.
+pub fn record_event_decoded(contract: Contract) {
+    metrics::counter!(EVENTS_DECODED, "contract" => contract.as_str()).increment(1);
+}
+
+/// Count a handler call by outcome.
+pub fn record_handler_result(handler: &'static str, success: bool) {
+    let outcome = if success { "success" } else { "failure" };
+    metrics::counter!(HANDLER_RESULTS, "handler" => handler, "outcome" => outcome).increment(1);
+}
+
+/// Count a log quarantined after `handler` failed on it.
+pub fn record_quarantined(handler: &'static str) {
+    metrics::counter!(EVENTS_QUARANTINED, "handler" => handler).increment(1);
+}
+
+/// Set how many logs of `chain_id` are in quarantine.
+#[allow(clippy::cast_precision_loss)] // Depth stays far below 2^52 logs
+pub fn set_quarantine_depth(chain_id: u64, depth: u64) {
+    metrics::gauge!(QUARANTINE_DEPTH, "chain" => chain_id.to_string()).set(depth as f64);
+}
+
+/// Record the latency of a store operation.
+pub fn record_store_latency(method: &'static str, elapsed: Duration) {
+    metrics::histogram!(STORE_LATENCY, "method" => method).record(elapsed.as_secs_f64());
+}
+
+/// Start timing a store operation; the latency is recorded when the returned
+/// timer is dropped.
+#[must_use]
+pub fn store_timer(method: &'static str) -> StoreTimer {
+    StoreTimer {
+        method,
+        started: Instant::now(),
+    }
+}
+
+/// Publish the cache's hit and miss counters.
+pub fn record_cache_stats(stats: &CacheStats) {
+    metrics::counter!(CACHE_HITS).absolute(stats.hits);
+    metrics::counter!(CACHE_MISSES).absolute(stats.misses);
+}
+
+/// Count messages delivered to an Iggy topic.
+pub fn record_published(topic: &str, count: usize) {
+    metrics::counter!(IGGY_PUBLISHED, "topic" => topic.to_string()).increment(count as u64);
+}
+
+/// Count messages sent to the dead-letter sink instead of their topic.
+pub fn record_dead_lettered(topic: &str, count: usize) {
+    metrics::counter!(IGGY_DEAD_LETTERED, "topic" => topic.to_string()).increment(count as u64);
+}
+
+/// Set how many events in the outbox of `chain_id` are not yet published.
+#[allow(clippy::cast_precision_loss)] // Depth stays far below 2^52 events
+pub fn set_outbox_depth(chain_id: u64, depth: u64) {
+    metrics::gauge!(OUTBOX_DEPTH, "chain" => chain_id.to_string()).set(depth as f64);
+}
+
+/// Count outbox events whose publish failed and will be retried.
+pub fn record_outbox_publish_failures(topic: &str, count: usize) {
+    metrics::counter!(OUTBOX_PUBLISH_FAILURES, "topic" => topic.to_string())
+        .increment(count as u64);
+}
+
+/// Set how many blocks the indexer is behind the head of `chain_id`.
+#[allow(clippy::cast_precision_loss)] // Lag stays far below 2^52 blocks
+pub fn set_lag_blocks(chain_id: u64, blocks: u64) {
+    metrics::gauge!(LAG_BLOCKS, "chain" => chain_id.to_string()).set(blocks as f64);
+}
+
+/// Set how many blocks the slowest contract cursor of `chain_id` trails the
+/// last indexed block.
+#[allow(clippy::cast_precision_loss)] // Lag stays far below 2^52 blocks
+pub fn set_cursor_lag_blocks(chain_id: u64, blocks: u64) {
+    metrics::gauge!(CURSOR_LAG_BLOCKS, "chain" => chain_id.to_string()).set(blocks as f64);
+}
+
+/// Count a config reload by outcome.
+pub fn record_config_reload(success: bool) {
+    let outcome = if success { "success" } else { "failure" };
+    metrics::counter!(CONFIG_RELOADS, "outcome" => outcome).increment(1);
+}
+
+/// Count a store operation retried after a connection error.
+pub fn record_store_retry(operation: &'static str) {
+    metrics::counter!(STORE_RETRIES, "operation" => operation).increment(1);
+}
+
+/// Publish the connection pool's usage.
+pub fn record_pool_health(pool: &PoolHealth) {
+    let in_use = pool.size.saturating_sub(pool.idle);
+    metrics::gauge!(POOL_CONNECTIONS, "state" => "in_use").set(f64::from(in_use));
+    metrics::gauge!(POOL_CONNECTIONS, "state" => "idle").set(f64::from(pool.idle));
+    metrics::gauge!(POOL_UTILIZATION).set(pool.utilization());
+}
+
+/// Set whether the pipeline is paused on an unavailable store.
+pub fn set_store_degraded(degraded: bool) {
+    metrics::gauge!(STORE_DEGRADED).set(if degraded { 1.0 } else { 0.0 });
+}
+
+/// Record how long the pipeline was paused on an unavailable store.
+pub fn record_store_outage(elapsed: Duration) {
+    metrics::histogram!(STORE_OUTAGE).record(elapsed.as_secs_f64());
+}
+
+/// Set how many addresses hold DATA.
+#[allow(clippy::cast_precision_loss)] // Holder counts stay far below 2^52
+pub fn set_token_holders(holders: u64) {
+    metrics::gauge!(TOKEN_HOLDERS).set(holders as f64);
+}
+
+/// Count an address that started holding DATA.
+pub fn add_token_holder() {
+    metrics::gauge!(TOKEN_HOLDERS).increment(1.0);
+}
+
+/// Count an address that stopped holding DATA.
+pub fn remove_token_holder() {
+    metrics::gauge!(TOKEN_HOLDERS).decrement(1.0);
+}
+
+/// Count a holder balance compared with the chain, by outcome.
+pub fn record_holder_reconciliation(outcome: &'static str) {
+    metrics::counter!(HOLDER_RECONCILIATIONS, "outcome" => outcome).increment(1);
+}
+
+/// Record by how many DATA a corrected holder balance was off.
+pub fn record_holder_drift(tokens: f64) {
+    metrics::histogram!(HOLDER_DRIFT).record(tokens);
+}
+
+/// Times a store operation, see [`store_timer`].
+#[derive(Debug)]
+pub struct StoreTimer {
+    method: &'static str,
+    started: Instant,
+}
+
+impl Drop for StoreTimer {
+    fn drop(&mut self) {
+        record_store_latency(self.method, self.started.elapsed());
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// EXPORTER
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Prometheus handle of the installed recorder.
+static HANDLE: OnceLock<PrometheusHandle> = OnceLock::new();
+
+/// Install the Prometheus recorder, once per process.
+///
+/// Later calls return the handle of the recorder installed first.
+pub fn install() -> PrometheusHandle {
+    HANDLE
+        .get_or_init(|| {
+            let builder = PrometheusBuilder::new()
+                .set_buckets_for_metric(Matcher::Full(STORE_LATENCY.into()), &STORE_LATENCY_BUCKETS)
+                .and_then(|builder| {
+                    builder.set_buckets_for_metric(
+                        Matcher::Full(HOLDER_DRIFT.into()),
+                        &HOLDER_DRIFT_BUCKETS,
+                    )
+                })
+                .unwrap_or_else(|_| PrometheusBuilder::new());
+            let recorder = builder.build_recorder();
+            let handle = recorder.handle();
+            if let Err(e) = metrics::set_global_recorder(recorder) {
+                warn!(error = %e, "Another metrics recorder is installed, not exporting metrics");
+            }
+            handle
+        })
+        .clone()
+}
+
+/// State of the metrics endpoint.
+#[derive(Clone)]
+struct MetricsState {
+    handle: PrometheusHandle,
+    cache: Option<Arc<dyn Cache>>,
+    store: Option<PostgresStore>,
+}
+
+/// Build the router serving `/metrics`.
+///
+/// With a `cache`, its hit and miss counters are published on each scrape;
+/// with a `store`, its connection pool usage.
+pub fn router(
+    handle: PrometheusHandle,
+    cache: Option<Arc<dyn Cache>>,
+    store: Option<PostgresStore>,
+) -> Router {
+    Router::new()
+        .route("/metrics", get(render))
+        .with_state(MetricsState {
+            handle,
+            cache,
+            store,
+        })
+}
+
+/// Render all metrics in the Prometheus text format.
+async fn render(State(state): State<MetricsState>) -> String {
+    if let Some(cache) = &state.cache {
+        record_cache_stats(&cache.stats());
+    }
+    if let Some(store) = &state.store {
+        record_pool_health(&store.pool_health());
+    }
+    state.handle.render()
+}
+
+/// Serve `router` on the configured metrics address until `shutdown` is
+/// cancelled.
+///
+/// # Errors
+///
+/// Returns an error if the address cannot be bound or the server fails.
+pub async fn serve(
+    settings: &MetricsSettings,
+    router: Router,
+    shutdown: CancellationToken,
+) -> Result<()> {
+    let addr = settings.socket_addr();
+    let listener = tokio::net::TcpListener::bind(&addr).await.map_err(|e| {
+        InfraError::Internal(format!("Failed to bind metrics server to {addr}: {e}"))
+    })?;
+    info!(%addr, "Metrics server listening");
+
+    axum::serve(listener, router)
+        .with_graceful_shutdown(async move { shutdown.cancelled().await })
+        .await
+        .map_err(|e| InfraError::Internal(format!("Metrics server error: {e}")).into())
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use alloy::primitives::{Address, B256, U256};
+    use alloy::rpc::types::Log;
+    use alloy::sol_types::SolEvent;
+    use axum::body::Body;
+    use axum::http::Request;
+    use chrono::Utc;
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::abi::data_token;
+    use crate::handlers::mocks::CountingHandler;
+    use crate::indexer::EventRouter;
+    use crate::store::MemoryCache;
+    use crate::types::events::EventMetadata;
+
+    /// Value of the first sample whose series starts with `series`.
+    fn sample(body: &str, series: &str) -> Option<f64> {
+        body.lines()
+            .filter(|line| !line.starts_with('#'))
+            .find(|line| line.starts_with(series))
+            .and_then(|line| line.rsplit(' ').next())
+            .and_then(|value| value.parse().ok())
+    }
+
+    async fn scrape(router: Router) -> String {
+        let request = Request::get("/metrics").body(Body::empty()).unwrap();
+        let response = router.oneshot(request).await.unwrap();
+        assert!(response.status().is_success());
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        String::from_utf8(body.to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn scrape_reports_pipeline_series() {
+        let handle = install();
+        let router = EventRouter::new(
+            CountingHandler::new(),
+            CountingHandler::new(),
+            CountingHandler::new(),
+            CountingHandler::new(),
+            CountingHandler::new(),
+            CountingHandler::new(),
+            CountingHandler::new(),
+        );
+
+        // A synthetic transfer, received over HTTP and routed
+        let transfer = data_token::Transfer {
+            from: Address::repeat_byte(1),
+            to: Address::repeat_byte(2),
+            value: U256::from(100),
+        };
+        let mut log = Log::default();
+        log.inner.data = transfer.encode_log_data();
+        let meta = EventMetadata {
+            block_number: 1,
+            block_hash: B256::ZERO,
+            tx_hash: B256::ZERO,
+            tx_index: 0,
+            log_index: 0,
+            timestamp: Utc::now(),
+            contract: Address::ZERO,
+            chain_id: 6343,
+            tx_function: None,
+            tx_from: None,
+        };
+        record_logs_received(LogSource::Http, 1);
+        assert!(router.route_log(&log, meta).await.unwrap());
+
+        drop(store_timer("save_round"));
+        record_published("ghostnet.events", 2);
+        set_lag_blocks(6343, 7);
+
+        let cache = Arc::new(MemoryCache::new());
+        let _ = cache.get_global_stats();
+        record_pool_health(&PoolHealth {
+            size: 4,
+            idle: 1,
+            max: 10,
+        });
+        set_store_degraded(false);
+        let body = scrape(super::router(handle, Some(cache), None)).await;
+
+        for series in [
+            r#"indexer_logs_received_total{source="http"}"#,
+            r#"indexer_events_decoded_total{contract="data_token"}"#,
+            r#"indexer_handler_results_total{handler="token",outcome="success"}"#,
+            r#"indexer_store_latency_seconds_count{method="save_round"}"#,
+            r#"indexer_iggy_published_total{topic="ghostnet.events"}"#,
+            "indexer_cache_misses_total",
+            r#"indexer_db_pool_connections{state="in_use"}"#,
+            "indexer_db_pool_utilization",
+        ] {
+            let value = sample(&body, series);
+            assert!(
+                value.is_some_and(|v| v > 0.0),
+                "{series} missing or zero:\n{body}"
+            );
+        }
+        // Gauges are last-write-wins, so other tests may have moved the lag
+        assert!(
+            sample(&body, r#"indexer_lag_blocks{chain="6343"}"#).is_some(),
+            "{body}"
+        );
+        assert!(sample(&body, "indexer_store_degraded").is_some(), "{body}");
+    }
+
+    #[test]
+    fn install_is_idempotent() {
+        let first = install();
+        let second = install();
+        record_logs_received(LogSource::Ws, 3);
+        assert!(
+            sample(
+                &second.render(),
+                r#"indexer_logs_received_total{source="ws"}"#
+            )
+            .is_some()
+        );
+        assert!(first.render().contains("indexer_logs_received_total"));
+    }
+}
@@ -178,6 +178,7 @@ impl Clone for FakeClock {
 // ═══════════════════════════════════════════════════════════════════════════════
 
 #[cfg(test)]
+#[allow(clippy::expect_used)]
 mod tests {
     use chrono::{Datelike, Duration, TimeZone, Timelike};
 
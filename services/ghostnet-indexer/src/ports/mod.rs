@@ -36,9 +36,10 @@
 //!
 //! | Category | Ports | Purpose |
 //! |----------|-------|---------|
-//! | Storage | [`PositionStore`], [`ScanStore`], [`DeathStore`], [`MarketStore`], [`IndexerStateStore`], [`StatsStore`] | Data persistence |
+//! | Storage | [`PositionStore`], [`ScanStore`], [`DeathStore`], [`MarketStore`], [`IndexerStateStore`], [`StatsStore`], [`LeaderboardStore`], [`TokenFlowStore`], [`HolderStore`], [`BoostStore`], [`EventOutboxStore`], [`EventLogStore`], [`RawLogStore`], [`QuarantineStore`] | Data persistence |
 //! | Streaming | [`EventPublisher`] | Event broadcasting |
 //! | Caching | [`Cache`] | In-memory caching |
+//! | Statistics | [`StatsSink`] | Aggregate stats deltas |
 //! | Time | [`Clock`] | Testable time operations |
 //!
 //! # Usage
@@ -69,14 +70,20 @@
 
 mod cache;
 mod clock;
+mod stats;
 mod store;
 mod streaming;
 
 // Re-export all port traits and types
 pub use cache::{Cache, CacheStats};
 pub use clock::{Clock, SystemClock};
-pub use store::{DeathStore, IndexerStateStore, MarketStore, PositionStore, ScanStore, StatsStore};
-pub use streaming::EventPublisher;
+pub use stats::StatsSink;
+pub use store::{
+    BoostStore, DeathStore, EventLogStore, EventOutboxStore, HolderStore, IndexerStateStore,
+    LeaderboardStore, MarketStore, PositionStore, QuarantineStore, RawLogStore, ScanStore,
+    StatsStore, TokenFlowStore,
+};
+pub use streaming::{EventPublisher, IdentifiedMessage};
 
 // Re-export test utilities for tests and downstream crates using test-utils feature
 #[cfg(any(test, feature = "test-utils"))]
@@ -89,6 +96,7 @@ mod tests {
     use super::*;
 
     #[test]
+    #[allow(dead_code)] // The checks only need to compile
     fn all_ports_are_send_sync() {
         // Compile-time check that all port traits require Send + Sync
         fn assert_send_sync<T: Send + Sync>() {}
@@ -112,6 +120,24 @@ mod tests {
         fn check_stats_store<T: StatsStore>() {
             assert_send_sync::<T>();
         }
+        fn check_leaderboard_store<T: LeaderboardStore>() {
+            assert_send_sync::<T>();
+        }
+        fn check_token_flow_store<T: TokenFlowStore>() {
+            assert_send_sync::<T>();
+        }
+        fn check_holder_store<T: HolderStore>() {
+            assert_send_sync::<T>();
+        }
+        fn check_event_outbox_store<T: EventOutboxStore>() {
+            assert_send_sync::<T>();
+        }
+        fn check_raw_log_store<T: RawLogStore>() {
+            assert_send_sync::<T>();
+        }
+        fn check_quarantine_store<T: QuarantineStore>() {
+            assert_send_sync::<T>();
+        }
         fn check_event_publisher<T: EventPublisher>() {
             assert_send_sync::<T>();
         }
@@ -121,5 +147,8 @@ mod tests {
         fn check_clock<T: Clock>() {
             assert_send_sync::<T>();
         }
+        fn check_stats_sink<T: StatsSink>() {
+            assert_send_sync::<T>();
+        }
     }
 }
@@ -0,0 +1,60 @@
+//! Statistics port for recording aggregate deltas.
+//!
+//! Handlers describe how an event changes the level, global and token flow
+//! statistics, and report finalized scans for the per-level history; the
+//! sink decides when those changes reach the store and the cache.
+
+use chrono::{DateTime, Utc};
+
+use crate::types::entities::{GlobalStatsDelta, LevelStatsDelta, TokenFlowDelta};
+use crate::types::enums::Level;
+use crate::types::primitives::BlockNumber;
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// STATS SINK
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Port for recording statistics deltas.
+///
+/// Recording is synchronous and must not block: implementations buffer
+/// deltas and persist them in batches. Implementations also own cache
+/// invalidation for the levels they are handed deltas for.
+///
+/// # Example
+///
+/// ```ignore
+/// use ghostnet_indexer::ports::StatsSink;
+/// use ghostnet_indexer::types::entities::LevelStatsDelta;
+///
+/// fn on_extracted<S: StatsSink>(stats: &S, position: &Position, at: DateTime<Utc>) {
+///     stats.record_level(position.level, LevelStatsDelta::extracted(position), at);
+/// }
+/// ```
+pub trait StatsSink: Send + Sync + std::fmt::Debug {
+    /// Record a change to a level's statistics.
+    ///
+    /// `at` is the event time, used for rolling windows such as deaths in
+    /// the last 24 hours.
+    fn record_level(&self, level: Level, delta: LevelStatsDelta, at: DateTime<Utc>);
+
+    /// Record a change to the global counters.
+    fn record_global(&self, delta: GlobalStatsDelta);
+
+    /// Record a change to the token flow aggregates.
+    ///
+    /// `at` is the event time, which selects the aggregate bucket.
+    fn record_token_flow(&self, delta: TokenFlowDelta, at: DateTime<Utc>);
+
+    /// Record a finalized scan for the level's history.
+    ///
+    /// Call after recording the scan's level delta: the survivors and stake
+    /// written with it are those of the level at that point.
+    fn record_scan(
+        &self,
+        level: Level,
+        scan_id: &str,
+        deaths: u32,
+        block: BlockNumber,
+        at: DateTime<Utc>,
+    );
+}
@@ -4,15 +4,24 @@
 //! domain entities. Infrastructure adapters implement these traits
 //! using concrete storage backends (e.g., PostgreSQL, SQLite).
 
-use alloy::primitives::B256;
+use std::time::Duration;
+
+use alloy::primitives::{B256, U256};
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 
+use super::streaming::IdentifiedMessage;
 use crate::error::Result;
+use crate::indexer::Contract;
 use crate::types::entities::{
-    Bet, Death, GlobalStats, LevelStats, LevelStatsDelta, Position, PositionHistoryEntry, Round,
-    Scan, ScanFinalizationData,
+    AddressFlows, Bet, Boost, BurnRate, CascadeEarnings, CascadeShare, Death, EffectiveStakeChange,
+    EventLogBounds, ExitStreakCount, GlobalStats, GlobalStatsDelta, HistoryBucket, HistoryCursor,
+    HolderBalance, HolderSort, LapsedBoost, LeaderboardEntry, LevelHistoryPoint, LevelScanStats,
+    LevelStats, LevelStatsDelta, LevelSurvival, LoggedEvent, OutboxEvent, OutboxRecord, Page,
+    Position, PositionFilter, PositionHistoryEntry, QuarantinedEvent, RawLog, Round,
+    RoundSettlement, Scan, ScanFinalizationData, TokenFlowDelta, TokenTransfer,
 };
-use crate::types::enums::Level;
+use crate::types::enums::{LeaderboardType, Level};
 use crate::types::primitives::{BlockNumber, EthAddress, TokenAmount};
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -68,15 +77,48 @@ pub trait PositionStore: Send + Sync {
     /// Returns an error if the database query fails.
     async fn get_at_risk_positions(&self, level: Level, threshold: u32) -> Result<Vec<Position>>;
 
-    /// Record a position history entry.
+    /// Append a position history entry.
     ///
     /// History entries track all changes to positions over time,
-    /// enabling audit trails and analytics.
+    /// enabling audit trails and analytics. Use
+    /// [`save_position_with_history`](Self::save_position_with_history) when
+    /// the position itself changes as well.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    async fn append_history(&self, entry: &PositionHistoryEntry) -> Result<()>;
+
+    /// Save a position, append the history entry describing the change and
+    /// add the change's `events` to the event outbox, atomically.
+    ///
+    /// Either all are written or none, so neither the history nor the
+    /// published events can diverge from the position row.
     ///
     /// # Errors
     ///
     /// Returns an error if the database operation fails.
-    async fn record_history(&self, entry: &PositionHistoryEntry) -> Result<()>;
+    async fn save_position_with_history(
+        &self,
+        position: &Position,
+        entry: &PositionHistoryEntry,
+        events: &[OutboxEvent],
+    ) -> Result<()>;
+
+    /// Get a user's position history, newest first.
+    ///
+    /// Returns at most `limit` entries older than `before`, across all of the
+    /// user's positions.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    async fn get_history(
+        &self,
+        address: &EthAddress,
+        limit: u32,
+        before: Option<HistoryCursor>,
+    ) -> Result<Vec<PositionHistoryEntry>>;
 
     /// Get position by ID.
     ///
@@ -92,12 +134,39 @@ pub trait PositionStore: Send + Sync {
     /// Returns an error if the database query fails.
     async fn get_positions_by_level(&self, level: Level) -> Result<Vec<Position>>;
 
+    /// List positions of all users matching `filter`, newest first.
+    ///
+    /// Returns at most `limit` positions, with a cursor for the next page if
+    /// more match.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    async fn query(&self, filter: &PositionFilter, limit: u32) -> Result<Page<Position>>;
+
     /// Count active positions for a level.
     ///
     /// # Errors
     ///
     /// Returns an error if the database query fails.
     async fn count_positions_by_level(&self, level: Level) -> Result<u32>;
+
+    /// Count the scans at `level` finalized after block `entered` and before
+    /// block `exited`.
+    ///
+    /// This is the number of scans a position that entered and exited in
+    /// those blocks survived. The scan finalized in the exit block is not
+    /// counted, since it may be the one that traced the position.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    async fn count_scans_survived(
+        &self,
+        level: Level,
+        entered: BlockNumber,
+        exited: BlockNumber,
+    ) -> Result<u32>;
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -119,11 +188,14 @@ pub trait PositionStore: Send + Sync {
 pub trait ScanStore: Send + Sync {
     /// Save a new scan record (Phase 1).
     ///
-    /// Called when a `ScanExecuted` event is received.
+    /// Called when a `ScanExecuted` event is received, or with a placeholder
+    /// (see [`Scan::is_placeholder`]) when a later lifecycle event arrives
+    /// first. An existing placeholder with the same on-chain ID is replaced;
+    /// an existing complete scan is left untouched.
     ///
     /// # Errors
     ///
-    /// Returns an error if the scan already exists or database fails.
+    /// Returns an error if the database operation fails.
     async fn save_scan(&self, scan: &Scan) -> Result<()>;
 
     /// Update scan with finalization data (Phase 2).
@@ -162,6 +234,20 @@ pub trait ScanStore: Send + Sync {
     ///
     /// Returns an error if the database query fails.
     async fn get_pending_scans(&self) -> Result<Vec<Scan>>;
+
+    /// Attach already recorded deaths to a scan.
+    ///
+    /// Returns the number of death records updated.
+    ///
+    /// # Arguments
+    ///
+    /// * `scan_id` - The on-chain scan ID (U256 as string)
+    /// * `death_ids` - IDs of the death records to link
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the scan doesn't exist or database fails.
+    async fn link_deaths_to_scan(&self, scan_id: &str, death_ids: &[uuid::Uuid]) -> Result<u64>;
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -218,6 +304,25 @@ pub trait DeathStore: Send + Sync {
     ///
     /// Returns an error if the database query fails.
     async fn get_recent_deaths(&self, limit: u32) -> Result<Vec<Death>>;
+
+    /// Record survivors' shares of a cascade distribution.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    async fn record_cascade_shares(&self, shares: &[CascadeShare]) -> Result<()>;
+
+    /// Get an address's cascade earnings: the all-time total and the `limit`
+    /// most recent scans it earned from.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    async fn get_cascade_earnings(
+        &self,
+        address: &EthAddress,
+        limit: u32,
+    ) -> Result<CascadeEarnings>;
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -237,39 +342,35 @@ pub trait DeathStore: Send + Sync {
 /// - Index on `is_resolved` for active round queries
 #[async_trait]
 pub trait MarketStore: Send + Sync {
-    /// Save a new betting round.
+    /// Save a betting round, replacing the stored one with the same on-chain
+    /// ID.
     ///
     /// # Errors
     ///
-    /// Returns an error if the round already exists or database fails.
+    /// Returns an error if the database fails.
     async fn save_round(&self, round: &Round) -> Result<()>;
 
     /// Record a bet on a round.
     ///
-    /// Should update round's pool totals atomically.
+    /// Should update round's pool totals atomically. A bet already recorded
+    /// at the same block and log index is ignored, pools included.
     ///
     /// # Errors
     ///
     /// Returns an error if the round doesn't exist or database fails.
     async fn record_bet(&self, bet: &Bet) -> Result<()>;
 
-    /// Resolve a round with outcome.
+    /// Resolve a round with its settlement.
     ///
     /// # Arguments
     ///
     /// * `round_id` - The on-chain round ID (U256 as string)
-    /// * `outcome` - `true` for OVER, `false` for UNDER
-    /// * `burned` - Amount of tokens burned as rake
+    /// * `settlement` - Outcome, rake, payout multiple and house take
     ///
     /// # Errors
     ///
     /// Returns an error if the round doesn't exist or is already resolved.
-    async fn resolve_round(
-        &self,
-        round_id: &str,
-        outcome: bool,
-        burned: &TokenAmount,
-    ) -> Result<()>;
+    async fn resolve_round(&self, round_id: &str, settlement: &RoundSettlement) -> Result<()>;
 
     /// Get active (unresolved) rounds.
     ///
@@ -285,7 +386,7 @@ pub trait MarketStore: Send + Sync {
     /// Returns an error if the database query fails.
     async fn get_round_by_id(&self, round_id: &str) -> Result<Option<Round>>;
 
-    /// Get bets for a round.
+    /// Get bets for a round, in the order they were placed.
     ///
     /// # Errors
     ///
@@ -328,11 +429,20 @@ pub trait MarketStore: Send + Sync {
 /// 3. Roll back state to fork point
 /// 4. Reprocess from fork point
 ///
+/// # Contract Cursors
+///
+/// Besides the last indexed block of the indexer as a whole, each contract
+/// has a cursor: the last block up to which all of its logs are indexed. A
+/// contract enabled after indexing started has no cursor, or one behind the
+/// others, and is backfilled on its own until it catches up.
+///
 /// # Implementation Notes
 ///
 /// Implementations should:
 /// - Keep a sliding window of recent block hashes (e.g., 256 blocks)
 /// - Use transactions for reorg rollback operations
+/// - Move cursors past the fork point back to it on reorg rollback
+/// - Hold batched writes until `commit_batch`, if they batch them
 #[async_trait]
 pub trait IndexerStateStore: Send + Sync {
     /// Get the last successfully indexed block number.
@@ -405,6 +515,67 @@ pub trait IndexerStateStore: Send + Sync {
     ///
     /// Returns an error if the database operation fails.
     async fn prune_old_blocks(&self, keep_blocks: u64) -> Result<u64>;
+
+    /// Get the last block up to which all logs of `contract` are indexed.
+    ///
+    /// Returns `None` if the contract has never been indexed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    async fn get_cursor(&self, contract: Contract) -> Result<Option<BlockNumber>>;
+
+    /// Set the cursor of `contract`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    async fn set_cursor(&self, contract: Contract, block: BlockNumber) -> Result<()>;
+
+    /// Get the lowest cursor of `contracts`, counting a contract without a
+    /// cursor as block 0.
+    ///
+    /// Disabled contracts keep their cursor, so callers pass the enabled
+    /// ones. Returns `None` if `contracts` is empty.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    async fn min_cursor(&self, contracts: &[Contract]) -> Result<Option<BlockNumber>>;
+
+    /// Commit the writes batched since the last commit.
+    ///
+    /// Stores that batch their writes hold them, on every port, until the
+    /// batch is committed; the pipeline commits at block boundaries, after
+    /// setting the checkpoint, so data and checkpoint persist together. The
+    /// default does nothing, for stores that write through.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the commit fails, in which case none of the
+    /// batched writes persist.
+    async fn commit_batch(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Discard the writes batched since the last commit.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the rollback fails.
+    async fn discard_batch(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Check that the store answers. The default always succeeds, for
+    /// stores that cannot become unreachable.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store cannot be reached.
+    async fn ping(&self) -> Result<()> {
+        Ok(())
+    }
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -463,4 +634,629 @@ pub trait StatsStore: Send + Sync {
     ///
     /// Returns an error if the refresh fails.
     async fn refresh_global_stats(&self) -> Result<GlobalStats>;
+
+    /// Update the event-sourced global counters with delta changes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    async fn update_global_stats(&self, delta: GlobalStatsDelta) -> Result<()>;
+
+    /// Rebuild level statistics from raw position, scan and death records.
+    ///
+    /// Overwrites the derivable counters (stake, alive, deaths, extractions,
+    /// culls, streaks, survival times). Deaths are counted from finalized scans plus death records
+    /// without a scan (system resets). Burned and distributed totals are only
+    /// known from events and are kept as-is.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the recomputation fails.
+    async fn recompute_level_stats(&self) -> Result<Vec<LevelStats>>;
+
+    /// Get death counts recorded since `since`, bucketed per level and minute.
+    ///
+    /// Used to seed rolling death windows after a restart.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    async fn get_recent_death_counts(
+        &self,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<(Level, DateTime<Utc>, u32)>>;
+
+    /// Get survival analytics of exited positions, one entry per level with
+    /// exits, ordered by level.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    async fn get_survival_stats(&self) -> Result<Vec<LevelSurvival>>;
+
+    /// Get the number of exits per level and ghost streak at exit, ordered
+    /// by level and streak.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    async fn get_exit_streak_distribution(&self) -> Result<Vec<ExitStreakCount>>;
+
+    /// Record the outcome of finalized scans at their levels.
+    ///
+    /// Idempotent: a scan that is already recorded is skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    async fn record_level_scans(&self, scans: &[LevelScanStats]) -> Result<()>;
+
+    /// Get the history of a level between `from` (inclusive) and `to`
+    /// (exclusive), one point per bucket including buckets without scans.
+    ///
+    /// See [`LevelHistoryPoint::series`] for how scans are aggregated.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    async fn get_level_history(
+        &self,
+        level: Level,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        bucket: HistoryBucket,
+    ) -> Result<Vec<LevelHistoryPoint>>;
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// LEADERBOARD STORE
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Port for leaderboard queries.
+///
+/// Leaderboards are expensive aggregations; callers are expected to cache
+/// the results and refresh them periodically rather than query per request.
+///
+/// # Implementation Notes
+///
+/// Implementations must:
+/// - Rank entries from 1, highest score first
+/// - Break ties by ascending wallet address so rankings are deterministic
+/// - List each wallet at most once per leaderboard
+#[async_trait]
+pub trait LeaderboardStore: Send + Sync {
+    /// Get the top `limit` entries for a leaderboard.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    async fn get_leaderboard(
+        &self,
+        leaderboard: LeaderboardType,
+        limit: u32,
+    ) -> Result<Vec<LeaderboardEntry>>;
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TOKEN FLOW STORE
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Port for `DataToken` flow analytics.
+///
+/// Raw transfers are optional (high volume); the hourly aggregates written
+/// by [`Self::record_token_flows`] back every windowed query.
+///
+/// # Implementation Notes
+///
+/// Implementations should:
+/// - Make `record_transfer` idempotent on `(tx_hash, log_index)` so replays
+///   do not duplicate rows
+/// - Apply a flow delta atomically (global bucket and per-address rows)
+/// - Include every bucket that overlaps the window, so windows have hour
+///   granularity
+#[async_trait]
+pub trait TokenFlowStore: Send + Sync {
+    /// Persist a raw transfer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    async fn record_transfer(&self, transfer: &TokenTransfer) -> Result<()>;
+
+    /// Add a flow delta to the aggregates for the hour starting at `bucket`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    async fn record_token_flows(&self, bucket: DateTime<Utc>, delta: &TokenFlowDelta)
+    -> Result<()>;
+
+    /// Burn and tax totals over the trailing `window`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    async fn get_burn_rate(&self, window: Duration) -> Result<BurnRate>;
+
+    /// Inflow and outflow of `address` over the trailing `window`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    async fn get_address_flows(
+        &self,
+        address: &EthAddress,
+        window: Duration,
+    ) -> Result<AddressFlows>;
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// HOLDER STORE
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Port for DATA holder balances.
+///
+/// The token handler reads and writes a holder per transfer side; the
+/// [`HolderReconciler`](crate::indexer::HolderReconciler) corrects them from
+/// `balanceOf`.
+///
+/// # Implementation Notes
+///
+/// Implementations should:
+/// - Only count and list addresses with a non-zero balance as holders
+/// - Make `reconcile_holder` conditional, so a correction read before a
+///   newer transfer was applied does not overwrite it
+/// - Flag holders touched past the fork point on reorg rollback, since the
+///   transfers of orphaned blocks stay in their balances
+#[async_trait]
+pub trait HolderStore: Send + Sync {
+    /// Get the balance of `address`, `None` if it never received DATA.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    async fn get_holder(&self, address: &EthAddress) -> Result<Option<HolderBalance>>;
+
+    /// Save a holder balance, replacing the previous one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    async fn save_holder(&self, holder: &HolderBalance) -> Result<()>;
+
+    /// Get up to `limit` holders in the order of `sort`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    async fn get_top_holders(&self, sort: HolderSort, limit: u32) -> Result<Vec<HolderBalance>>;
+
+    /// Count the addresses holding DATA.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    async fn count_holders(&self) -> Result<u64>;
+
+    /// Get up to `limit` holders to compare with the chain: flagged ones
+    /// first, then those compared longest ago.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    async fn get_holders_to_reconcile(&self, limit: u32) -> Result<Vec<HolderBalance>>;
+
+    /// Set the balance of `holder` as read from the chain at `at`, and clear
+    /// its flag.
+    ///
+    /// Nothing is written if a transfer was applied to the holder since it
+    /// was read, i.e. its last transfer is no longer the one in `holder`.
+    /// Returns whether the balance was written.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    async fn reconcile_holder(
+        &self,
+        holder: &HolderBalance,
+        balance: U256,
+        at: DateTime<Utc>,
+    ) -> Result<bool>;
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// BOOST STORE
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Port for boosts applied to positions.
+///
+/// The position handler saves a boost per `BoostApplied` event; the
+/// [`BoostExpirer`](crate::indexer::BoostExpirer) records them as expired
+/// once they lapse or their position closes.
+///
+/// # Implementation Notes
+///
+/// Implementations should:
+/// - Save each `BoostApplied` log once, so a replayed event is not a second
+///   boost
+/// - Treat a boost whose position closed as no longer active, whether or
+///   not it was recorded as expired yet
+#[async_trait]
+pub trait BoostStore: Send + Sync {
+    /// Save a new boost, unless its `BoostApplied` log was saved before.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    async fn save_boost(&self, boost: &Boost) -> Result<()>;
+
+    /// Get the boosts of `address` active at `now`, soonest expiry first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    async fn get_active_boosts(
+        &self,
+        address: &EthAddress,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<Boost>>;
+
+    /// Get up to `limit` boosts active at `now` that expire before `before`,
+    /// soonest expiry first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    async fn get_expiring_boosts(
+        &self,
+        now: DateTime<Utc>,
+        before: DateTime<Utc>,
+        limit: u32,
+    ) -> Result<Vec<Boost>>;
+
+    /// Get up to `limit` boosts not recorded as expired that expired by
+    /// `now` or whose position closed, earliest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    async fn get_lapsed_boosts(&self, now: DateTime<Utc>, limit: u32) -> Result<Vec<LapsedBoost>>;
+
+    /// Record boosts as expired, each with when it stopped applying.
+    ///
+    /// Boosts already recorded as expired keep their time. Returns the
+    /// number of boosts recorded.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    async fn expire_boosts(&self, expired: &[(uuid::Uuid, DateTime<Utc>)]) -> Result<u64>;
+
+    /// Weight the stakes of the active positions among `positions` by the
+    /// yield multipliers active on them at `now`, as
+    /// [`Position::apply_boosts`] does.
+    ///
+    /// Returns the positions whose effective stake changed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    async fn reweigh_positions(
+        &self,
+        positions: &[uuid::Uuid],
+        now: DateTime<Utc>,
+    ) -> Result<Vec<EffectiveStakeChange>>;
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// EVENT OUTBOX STORE
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Port for the transactional event outbox.
+///
+/// Handlers add events to the outbox in the same transaction as the state
+/// change they describe (e.g. through
+/// [`PositionStore::save_position_with_history`]), and the
+/// [`OutboxRelay`](crate::streaming::OutboxRelay) publishes them afterwards.
+/// A crash between the two leaves the events in the outbox rather than
+/// losing them, and rolled back changes never publish theirs.
+///
+/// # Ordering
+///
+/// Entries are published in ID order within their aggregate. An entry is not
+/// handed out while an earlier entry of its aggregate waits for a retry, so
+/// a failing entry holds back the rest of its aggregate but no other
+/// aggregate. One relay publishes each outbox.
+///
+/// # Implementation Notes
+///
+/// Implementations should:
+/// - Assign increasing IDs in insert order (writes to one aggregate are
+///   sequential, so this is its commit order)
+/// - Index unpublished entries by `(aggregate, id)`
+/// - Remove unpublished entries past the fork point on reorg rollback
+#[async_trait]
+pub trait EventOutboxStore: Send + Sync {
+    /// Add events to the outbox, outside of any other change.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    async fn enqueue_events(&self, events: &[OutboxEvent]) -> Result<()>;
+
+    /// Get up to `limit` unpublished entries due at `now`, in ID order.
+    ///
+    /// Entries waiting for their retry are skipped, and so are the later
+    /// entries of their aggregate.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    async fn fetch_unpublished(&self, limit: u32, now: DateTime<Utc>) -> Result<Vec<OutboxRecord>>;
+
+    /// Mark entries as published at `at`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    async fn mark_published(&self, ids: &[i64], at: DateTime<Utc>) -> Result<()>;
+
+    /// Record a failed publish of an entry, retrying it no earlier than
+    /// `retry_at`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    async fn record_publish_failure(
+        &self,
+        id: i64,
+        error: &str,
+        retry_at: DateTime<Utc>,
+    ) -> Result<()>;
+
+    /// Delete entries published before `before`, returning how many.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    async fn purge_published(&self, before: DateTime<Utc>) -> Result<u64>;
+
+    /// Count unpublished entries.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    async fn outbox_depth(&self) -> Result<u64>;
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// EVENT LOG STORE
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Port for the sequenced event log replayed to WebSocket clients.
+///
+/// The [`EventLog`](crate::streaming::EventLog) logs each message before it
+/// is published, which assigns its sequence, and marks it published once
+/// the streaming system accepted it. Clients resuming from a sequence are
+/// replayed the published events from there on.
+///
+/// # Implementation Notes
+///
+/// Implementations should:
+/// - Assign each topic's sequences from 1 without gaps, in message order
+/// - Keep the sequence of a message ID logged again
+/// - Delete a prefix of each topic's sequences on purge, never the latest
+///   published event
+#[async_trait]
+pub trait EventLogStore: Send + Sync {
+    /// Log `messages` of `topic` unpublished, returning their sequences in
+    /// message order.
+    ///
+    /// A message whose ID was logged before keeps its sequence.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    async fn log_events(&self, topic: &str, messages: &[IdentifiedMessage]) -> Result<Vec<u64>>;
+
+    /// Mark logged events of `topic` as published at `at`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    async fn mark_events_published(
+        &self,
+        topic: &str,
+        sequences: &[u64],
+        at: DateTime<Utc>,
+    ) -> Result<()>;
+
+    /// Get up to `limit` published events of `topic` from `from_sequence`
+    /// on, in sequence order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    async fn get_logged_events(
+        &self,
+        topic: &str,
+        from_sequence: u64,
+        limit: u32,
+    ) -> Result<Vec<LoggedEvent>>;
+
+    /// Oldest kept and latest published sequence of `topic`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    async fn event_log_bounds(&self, topic: &str) -> Result<EventLogBounds>;
+
+    /// Delete, per topic, the published events before `before` and all but
+    /// the latest `keep` published events, returning how many.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    async fn purge_event_log(&self, before: DateTime<Utc>, keep: u64) -> Result<u64>;
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// RAW LOG STORE
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Port for the raw log archive, and for replaying it.
+///
+/// The [`Pipeline`](crate::indexer::Pipeline) archives each log before routing
+/// it; the [`LogReplayer`](crate::indexer::LogReplayer) reads them back to run
+/// the handlers again without refetching from RPC.
+///
+/// # Implementation Notes
+///
+/// Implementations should:
+/// - Make `archive_logs` idempotent on `(block_number, tx_index, log_index)`,
+///   so a block indexed twice is archived once
+/// - Hold archived logs until `commit_batch`, if they batch writes, so the
+///   archive matches what the handlers committed
+/// - Remove logs past the fork point on reorg rollback
+#[async_trait]
+pub trait RawLogStore: Send + Sync {
+    /// Archive logs as received, before they are decoded.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    async fn archive_logs(&self, logs: &[RawLog]) -> Result<()>;
+
+    /// Get up to `limit` archived logs from blocks `from..=to`, in chain order.
+    ///
+    /// With `after`, only logs after that `(block, tx_index, log_index)` are
+    /// returned, so a range is read in pages.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    async fn get_raw_logs(
+        &self,
+        from: BlockNumber,
+        to: BlockNumber,
+        after: Option<(BlockNumber, u64, u64)>,
+        limit: u32,
+    ) -> Result<Vec<RawLog>>;
+
+    /// First and last block with archived logs, if any are archived.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    async fn raw_log_range(&self) -> Result<Option<(BlockNumber, BlockNumber)>>;
+
+    /// Get the last block fully replayed by the unfinished `replay`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    async fn get_replay_progress(&self, replay: &str) -> Result<Option<BlockNumber>>;
+
+    /// Record that `replay` has replayed every log up to `block`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    async fn set_replay_progress(&self, replay: &str, block: BlockNumber) -> Result<()>;
+
+    /// Forget the progress of `replay`, once it has finished.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    async fn clear_replay_progress(&self, replay: &str) -> Result<()>;
+
+    /// Delete the tables derived by `handlers`, named as by
+    /// [`EventKind::handler`](crate::indexer::EventKind::handler).
+    ///
+    /// Handlers without tables of their own are skipped. Returns the number
+    /// of rows deleted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    async fn clear_derived(&self, handlers: &[&str]) -> Result<u64>;
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// QUARANTINE STORE
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Port for logs set aside after their handler failed on them.
+///
+/// The [`Quarantine`](crate::indexer::Quarantine) writes a log here instead
+/// of failing the pipeline; `ghostnet-indexer quarantine retry` reads it back
+/// to route it again once the handler is fixed.
+///
+/// # Implementation Notes
+///
+/// Implementations should:
+/// - Write straight to the database, never into a pending batch: a
+///   quarantined log is recorded even when the batch it failed in is
+///   discarded
+/// - Make `quarantine_event` idempotent on `(block_number, tx_index,
+///   log_index)`, updating the error of a log quarantined again
+/// - Remove events past the fork point on reorg rollback
+#[async_trait]
+pub trait QuarantineStore: Send + Sync {
+    /// Quarantine `log` after `handler` failed on it with `error`.
+    ///
+    /// `aggregate` is the entity the log belongs to, for strict ordering;
+    /// `blocked_by` the earlier quarantined event of that aggregate, if the
+    /// log was only quarantined for coming after it. Returns the ID of the
+    /// quarantined event.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    async fn quarantine_event(
+        &self,
+        log: &RawLog,
+        handler: &str,
+        aggregate: Option<B256>,
+        error: &str,
+        blocked_by: Option<i64>,
+    ) -> Result<i64>;
+
+    /// Get every quarantined event, in chain order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    async fn list_quarantined(&self) -> Result<Vec<QuarantinedEvent>>;
+
+    /// Get a quarantined event by ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    async fn get_quarantined(&self, id: i64) -> Result<Option<QuarantinedEvent>>;
+
+    /// Record a failed retry of a quarantined event, with its `error`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    async fn record_quarantine_attempt(&self, id: i64, error: &str) -> Result<()>;
+
+    /// Release a quarantined event once it has been handled.
+    ///
+    /// Returns whether the event was still quarantined.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    async fn release_quarantined(&self, id: i64) -> Result<bool>;
+
+    /// Number of quarantined events.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    async fn quarantine_depth(&self) -> Result<u64>;
 }
@@ -4,6 +4,7 @@
 //! (e.g., Apache Iggy, Kafka, Redis Streams).
 
 use async_trait::async_trait;
+use bytes::Bytes;
 
 use crate::error::Result;
 use crate::types::events::GhostnetEvent;
@@ -12,6 +13,21 @@ use crate::types::events::GhostnetEvent;
 // EVENT PUBLISHER
 // ═══════════════════════════════════════════════════════════════════════════════
 
+/// Message with a stable ID, for [`EventPublisher::publish_acknowledged`].
+///
+/// The ID stays the same when the message is published again, so consumers
+/// (or the streaming system) can drop redeliveries.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdentifiedMessage {
+    /// Stable message ID, e.g. the outbox entry ID.
+    pub id: u128,
+    /// Message payload.
+    pub payload: Bytes,
+    /// Position of the message within its topic, assigned by the
+    /// [`EventLog`](crate::streaming::EventLog); `None` until it is logged.
+    pub sequence: Option<u64>,
+}
+
 /// Port for event streaming/publishing.
 ///
 /// Publishes events to a streaming system for real-time consumption
@@ -28,6 +44,16 @@ use crate::types::events::GhostnetEvent;
 /// | `ghostnet.deaths` | DeathsProcessed, SurvivorsUpdated |
 /// | `ghostnet.market` | RoundCreated, BetPlaced, RoundResolved |
 /// | `ghostnet.system` | SystemResetTriggered |
+/// | `ghostnet.derived` | RoundClosingSoon, RoundAwaitingResolution, BoostExpired |
+///
+/// # Delivery
+///
+/// [`publish`](Self::publish) and friends are fire-and-forget: messages may
+/// be buffered, and batches that keep failing may be dead-lettered, so a
+/// successful call does not mean the message was delivered.
+/// [`publish_acknowledged`](Self::publish_acknowledged) returns only once
+/// the streaming system accepted the messages; the outbox relay publishes
+/// through it.
 ///
 /// # Implementation Notes
 ///
@@ -70,6 +96,19 @@ pub trait EventPublisher: Send + Sync {
     /// document whether partial batches are committed.
     async fn publish_batch(&self, events: &[GhostnetEvent]) -> Result<()>;
 
+    /// Publish messages to a topic, in order, and wait for the streaming
+    /// system to accept them.
+    ///
+    /// Nothing is buffered and nothing is dead-lettered: `Ok` means every
+    /// message was delivered. On error some may have been delivered anyway,
+    /// so retries must publish the same IDs again.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the messages could not be delivered.
+    async fn publish_acknowledged(&self, topic: &str, messages: &[IdentifiedMessage])
+    -> Result<()>;
+
     /// Flush pending messages.
     ///
     /// Ensures all buffered messages are sent before returning.
@@ -169,6 +208,21 @@ pub mod mocks {
             Ok(())
         }
 
+        async fn publish_acknowledged(
+            &self,
+            _topic: &str,
+            messages: &[IdentifiedMessage],
+        ) -> Result<()> {
+            if self.should_fail.load(Ordering::SeqCst) {
+                return Err(crate::error::AppError::Infra(
+                    crate::error::InfraError::Streaming("Mock publish failure".into()),
+                ));
+            }
+            self.publish_count
+                .fetch_add(messages.len(), Ordering::SeqCst);
+            Ok(())
+        }
+
         async fn flush(&self) -> Result<()> {
             Ok(())
         }
@@ -196,4 +250,15 @@ mod tests {
         publisher.set_connected(false);
         assert!(!publisher.is_connected());
     }
+
+    #[tokio::test]
+    async fn mock_publisher_fails_on_demand() {
+        let publisher = MockEventPublisher::new();
+        publisher.set_should_fail(true);
+        assert!(publisher.publish_to_topic("scans", b"1").await.is_err());
+
+        publisher.set_should_fail(false);
+        assert!(publisher.publish_to_topic("scans", b"1").await.is_ok());
+        assert_eq!(publisher.count(), 1);
+    }
 }
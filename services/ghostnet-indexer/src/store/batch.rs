@@ -0,0 +1,452 @@
+//! Write batching for [`PostgresStore`](super::PostgresStore).
+//!
+//! Handlers write each event on its own, which at thousands of events per
+//! second means thousands of tiny transactions. A batched store (see
+//! [`PostgresStore::batched`](super::PostgresStore::batched)) runs all of its
+//! statements in one transaction instead, opened by the first statement and
+//! committed by [`IndexerStateStore::commit_batch`]. The
+//! [`Pipeline`](crate::indexer::Pipeline) commits at block boundaries, after
+//! writing the checkpoint into the same transaction, so the data on disk never
+//! runs ahead of the recorded cursor.
+//!
+//! # Bulk Tables
+//!
+//! Rows of the high-volume tables are not written one statement at a time but
+//! buffered, and inserted with one `UNNEST` statement per table:
+//!
+//! | Table | Written by |
+//! |-------|------------|
+//! | `position_history` | `append_history`, `save_position_with_history` |
+//! | `deaths` | `record_deaths` |
+//! | `token_transfers` | `record_transfer` |
+//! | `raw_logs` | `archive_logs` |
+//!
+//! The buffers are flushed when the batch commits, and before any statement
+//! that reads or updates these tables, so the batch always reads its own
+//! writes.
+//!
+//! # Failures
+//!
+//! A failed statement aborts the transaction: every later statement of the
+//! batch fails too, and committing it fails and rolls everything back. The
+//! store never commits part of a batch.
+//!
+//! [`IndexerStateStore::commit_batch`]: crate::ports::IndexerStateStore::commit_batch
+
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+
+use sqlx::pool::PoolConnection;
+use sqlx::postgres::{PgConnection, PgPool};
+use sqlx::types::BigDecimal;
+use sqlx::{Postgres, Transaction};
+use tokio::sync::{MappedMutexGuard, Mutex, MutexGuard};
+use tracing::debug;
+use uuid::Uuid;
+
+use crate::error::{InfraError, Result};
+use crate::types::entities::{Death, PositionHistoryEntry, RawLog, TokenTransfer};
+use crate::types::primitives::TokenAmount;
+
+use super::retry::RetryPolicy;
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// CONNECTIONS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Connection a store statement runs on.
+pub(super) enum Conn<'a> {
+    /// Connection from the pool, for stores that write through.
+    Pooled(PoolConnection<Postgres>),
+    /// Transaction of its own, for multi-statement writes of stores that
+    /// write through.
+    Transaction(Transaction<'static, Postgres>),
+    /// The open transaction of a batched store.
+    Batched(MappedMutexGuard<'a, Transaction<'static, Postgres>>),
+}
+
+impl Conn<'_> {
+    /// Commit the connection's own transaction.
+    ///
+    /// Statements on a batched store's transaction are committed with the
+    /// batch, so this does nothing for them.
+    pub(super) async fn commit(self) -> Result<()> {
+        if let Self::Transaction(tx) = self {
+            tx.commit().await.map_err(InfraError::Database)?;
+        }
+        Ok(())
+    }
+}
+
+impl Deref for Conn<'_> {
+    type Target = PgConnection;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            Self::Pooled(conn) => conn,
+            Self::Transaction(tx) => tx,
+            Self::Batched(tx) => tx,
+        }
+    }
+}
+
+impl DerefMut for Conn<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        match self {
+            Self::Pooled(conn) => conn,
+            Self::Transaction(tx) => tx,
+            Self::Batched(tx) => tx,
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// WRITE BATCH
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// The transaction and buffered rows of a batched store.
+#[derive(Default)]
+pub(super) struct WriteBatch {
+    state: Mutex<BatchState>,
+}
+
+#[derive(Default)]
+struct BatchState {
+    /// Open transaction, begun by the batch's first statement.
+    tx: Option<Transaction<'static, Postgres>>,
+    /// Rows of the bulk tables not yet inserted.
+    rows: BulkRows,
+}
+
+impl fmt::Debug for WriteBatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("WriteBatch");
+        match self.state.try_lock() {
+            Ok(state) => debug
+                .field("open", &state.tx.is_some())
+                .field("buffered_rows", &state.rows.len())
+                .finish(),
+            Err(_) => debug.finish_non_exhaustive(),
+        }
+    }
+}
+
+impl WriteBatch {
+    /// The batch's transaction, begun on `pool` if none is open.
+    ///
+    /// With `flush`, the buffered rows are inserted first, for statements
+    /// touching the bulk tables.
+    pub(super) async fn conn(
+        &self,
+        pool: &PgPool,
+        retry: &RetryPolicy,
+        flush: bool,
+    ) -> Result<Conn<'_>> {
+        let mut state = self.state.lock().await;
+        if state.tx.is_none() {
+            state.tx = Some(retry.begin(pool).await?);
+        }
+        if flush {
+            let BatchState { tx, rows } = &mut *state;
+            if let Some(tx) = tx {
+                rows.insert(tx).await?;
+            }
+        }
+        MutexGuard::try_map(state, |state| state.tx.as_mut())
+            .map(Conn::Batched)
+            .map_err(|_| InfraError::Internal("Write batch has no transaction".into()).into())
+    }
+
+    /// Buffer rows of the bulk tables, inserted when next flushed.
+    pub(super) async fn buffer(&self, add: impl FnOnce(&mut BulkRows) + Send) {
+        add(&mut self.state.lock().await.rows);
+    }
+
+    /// Insert the buffered rows and commit the transaction.
+    ///
+    /// On failure the whole batch is rolled back, as the transaction is
+    /// dropped uncommitted.
+    pub(super) async fn commit(&self, pool: &PgPool, retry: &RetryPolicy) -> Result<()> {
+        let mut state = self.state.lock().await;
+        let rows = std::mem::take(&mut state.rows);
+        let mut tx = match state.tx.take() {
+            Some(tx) => tx,
+            None if rows.is_empty() => return Ok(()),
+            None => retry.begin(pool).await?,
+        };
+        drop(state);
+
+        let buffered = rows.len();
+        let mut rows = rows;
+        rows.insert(&mut tx).await?;
+        // An aborted transaction answers COMMIT with a silent rollback
+        sqlx::query("SELECT 1")
+            .execute(&mut *tx)
+            .await
+            .map_err(InfraError::Database)?;
+        tx.commit().await.map_err(InfraError::Database)?;
+
+        debug!(buffered, "Write batch committed");
+        Ok(())
+    }
+
+    /// Roll back the transaction and drop the buffered rows.
+    pub(super) async fn rollback(&self) -> Result<()> {
+        let mut state = self.state.lock().await;
+        state.rows = BulkRows::default();
+        let Some(tx) = state.tx.take() else {
+            return Ok(());
+        };
+        drop(state);
+
+        tx.rollback().await.map_err(InfraError::Database)?;
+        debug!("Write batch rolled back");
+        Ok(())
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// BULK ROWS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Buffered rows of the bulk tables.
+#[derive(Debug, Default)]
+pub(super) struct BulkRows {
+    pub(super) history: Vec<PositionHistoryEntry>,
+    pub(super) deaths: Vec<Death>,
+    pub(super) transfers: Vec<TokenTransfer>,
+    pub(super) raw_logs: Vec<RawLog>,
+}
+
+impl BulkRows {
+    const fn len(&self) -> usize {
+        self.history.len() + self.deaths.len() + self.transfers.len() + self.raw_logs.len()
+    }
+
+    const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Insert and clear the buffered rows.
+    async fn insert(&mut self, conn: &mut PgConnection) -> Result<()> {
+        insert_history(&mut *conn, &std::mem::take(&mut self.history)).await?;
+        insert_deaths(&mut *conn, &std::mem::take(&mut self.deaths)).await?;
+        insert_transfers(&mut *conn, &std::mem::take(&mut self.transfers)).await?;
+        insert_raw_logs(conn, &std::mem::take(&mut self.raw_logs)).await
+    }
+}
+
+/// Insert position history rows with one statement.
+async fn insert_history(conn: &mut PgConnection, entries: &[PositionHistoryEntry]) -> Result<()> {
+    if entries.is_empty() {
+        return Ok(());
+    }
+    sqlx::query(
+        r#"
+        INSERT INTO position_history (
+            id, position_id, user_address, action, amount_change, new_total,
+            level, ghost_streak, block_number, timestamp
+        )
+        SELECT * FROM UNNEST(
+            $1::uuid[], $2::uuid[], $3::bytea[], $4::varchar[], $5::numeric[], $6::numeric[],
+            $7::smallint[], $8::integer[], $9::bigint[], $10::timestamptz[]
+        )
+        "#,
+    )
+    .bind(entries.iter().map(|e| e.id).collect::<Vec<_>>())
+    .bind(entries.iter().map(|e| e.position_id).collect::<Vec<_>>())
+    .bind(
+        entries
+            .iter()
+            .map(|e| e.user_address.as_bytes().to_vec())
+            .collect::<Vec<_>>(),
+    )
+    .bind(entries.iter().map(|e| e.action.name()).collect::<Vec<_>>())
+    .bind(decimals(entries.iter().map(|e| &e.amount_change)))
+    .bind(decimals(entries.iter().map(|e| &e.new_total)))
+    .bind(entries.iter().map(|e| e.level as i16).collect::<Vec<_>>())
+    .bind(
+        entries
+            .iter()
+            .map(|e| e.ghost_streak.value())
+            .collect::<Vec<_>>(),
+    )
+    .bind(
+        entries
+            .iter()
+            .map(|e| block_number(e.block_number.value()))
+            .collect::<Vec<_>>(),
+    )
+    .bind(entries.iter().map(|e| e.timestamp).collect::<Vec<_>>())
+    .execute(conn)
+    .await
+    .map_err(InfraError::Database)?;
+    Ok(())
+}
+
+/// Insert death rows with one statement.
+pub(super) async fn insert_deaths(conn: &mut PgConnection, deaths: &[Death]) -> Result<()> {
+    if deaths.is_empty() {
+        return Ok(());
+    }
+    sqlx::query(
+        r#"
+        INSERT INTO deaths (
+            id, scan_id, user_address, position_id, amount_lost,
+            level, ghost_streak_at_death, created_at
+        )
+        SELECT * FROM UNNEST(
+            $1::uuid[], $2::uuid[], $3::bytea[], $4::uuid[], $5::numeric[],
+            $6::smallint[], $7::integer[], $8::timestamptz[]
+        )
+        "#,
+    )
+    .bind(deaths.iter().map(|d| d.id).collect::<Vec<_>>())
+    .bind(
+        deaths
+            .iter()
+            .map(|d| d.scan_id)
+            .collect::<Vec<Option<Uuid>>>(),
+    )
+    .bind(
+        deaths
+            .iter()
+            .map(|d| d.user_address.as_bytes().to_vec())
+            .collect::<Vec<_>>(),
+    )
+    .bind(
+        deaths
+            .iter()
+            .map(|d| d.position_id)
+            .collect::<Vec<Option<Uuid>>>(),
+    )
+    .bind(decimals(deaths.iter().map(|d| &d.amount_lost)))
+    .bind(deaths.iter().map(|d| d.level as i16).collect::<Vec<_>>())
+    .bind(
+        deaths
+            .iter()
+            .map(|d| d.ghost_streak_at_death.map(|s| s.value()))
+            .collect::<Vec<_>>(),
+    )
+    .bind(deaths.iter().map(|d| d.created_at).collect::<Vec<_>>())
+    .execute(conn)
+    .await
+    .map_err(InfraError::Database)?;
+    Ok(())
+}
+
+/// Insert token transfer rows with one statement, skipping known transfers.
+async fn insert_transfers(conn: &mut PgConnection, transfers: &[TokenTransfer]) -> Result<()> {
+    if transfers.is_empty() {
+        return Ok(());
+    }
+    sqlx::query(
+        r#"
+        INSERT INTO token_transfers (
+            tx_hash, log_index, from_address, to_address, amount, block_number, timestamp
+        )
+        SELECT * FROM UNNEST(
+            $1::bytea[], $2::integer[], $3::bytea[], $4::bytea[], $5::numeric[],
+            $6::bigint[], $7::timestamptz[]
+        )
+        ON CONFLICT (timestamp, tx_hash, log_index) DO NOTHING
+        "#,
+    )
+    .bind(
+        transfers
+            .iter()
+            .map(|t| t.tx_hash.to_vec())
+            .collect::<Vec<_>>(),
+    )
+    .bind(
+        transfers
+            .iter()
+            .map(|t| i32::try_from(t.log_index).unwrap_or(i32::MAX))
+            .collect::<Vec<_>>(),
+    )
+    .bind(
+        transfers
+            .iter()
+            .map(|t| t.from_address.as_bytes().to_vec())
+            .collect::<Vec<_>>(),
+    )
+    .bind(
+        transfers
+            .iter()
+            .map(|t| t.to_address.as_bytes().to_vec())
+            .collect::<Vec<_>>(),
+    )
+    .bind(decimals(transfers.iter().map(|t| &t.amount)))
+    .bind(
+        transfers
+            .iter()
+            .map(|t| block_number(t.block_number.value()))
+            .collect::<Vec<_>>(),
+    )
+    .bind(transfers.iter().map(|t| t.timestamp).collect::<Vec<_>>())
+    .execute(conn)
+    .await
+    .map_err(InfraError::Database)?;
+    Ok(())
+}
+
+/// Insert raw log rows with one statement, skipping archived logs.
+pub(super) async fn insert_raw_logs(conn: &mut PgConnection, logs: &[RawLog]) -> Result<()> {
+    if logs.is_empty() {
+        return Ok(());
+    }
+    sqlx::query(
+        r#"
+        INSERT INTO raw_logs (
+            block_number, tx_index, log_index, block_hash, tx_hash, address,
+            topics, data, timestamp, tx_function, tx_from, chain_id
+        )
+        SELECT * FROM UNNEST(
+            $1::bigint[], $2::bigint[], $3::bigint[], $4::bytea[], $5::bytea[], $6::bytea[],
+            $7::bytea[], $8::bytea[], $9::timestamptz[], $10::text[], $11::bytea[],
+            $12::bigint[]
+        )
+        ON CONFLICT (block_number, tx_index, log_index) DO NOTHING
+        "#,
+    )
+    .bind(
+        logs.iter()
+            .map(|l| block_number(l.block_number.value()))
+            .collect::<Vec<_>>(),
+    )
+    .bind(logs.iter().map(|l| block_number(l.tx_index)).collect::<Vec<_>>())
+    .bind(logs.iter().map(|l| block_number(l.log_index)).collect::<Vec<_>>())
+    .bind(logs.iter().map(|l| l.block_hash.to_vec()).collect::<Vec<_>>())
+    .bind(logs.iter().map(|l| l.tx_hash.to_vec()).collect::<Vec<_>>())
+    .bind(logs.iter().map(|l| l.address.to_vec()).collect::<Vec<_>>())
+    .bind(logs.iter().map(|l| l.topics.concat()).collect::<Vec<_>>())
+    .bind(logs.iter().map(|l| l.data.to_vec()).collect::<Vec<_>>())
+    .bind(logs.iter().map(|l| l.timestamp).collect::<Vec<_>>())
+    .bind(
+        logs.iter()
+            .map(|l| l.tx_function.clone())
+            .collect::<Vec<Option<String>>>(),
+    )
+    .bind(
+        logs.iter()
+            .map(|l| l.tx_from.map(|from| from.to_vec()))
+            .collect::<Vec<Option<Vec<u8>>>>(),
+    )
+    .bind(
+        logs.iter()
+            .map(|l| block_number(l.chain_id))
+            .collect::<Vec<_>>(),
+    )
+    .execute(conn)
+    .await
+    .map_err(InfraError::Database)?;
+    Ok(())
+}
+
+fn decimals<'a>(amounts: impl Iterator<Item = &'a TokenAmount>) -> Vec<BigDecimal> {
+    amounts.map(TokenAmount::to_bigdecimal).collect()
+}
+
+fn block_number(block: u64) -> i64 {
+    i64::try_from(block).unwrap_or(i64::MAX)
+}
@@ -32,6 +32,12 @@
 //! | Level Stats | 1 min | 5 | Per-level metrics, one per level |
 //! | Leaderboards | 5 min | 20 | Expensive queries, different types |
 //! | Block Hashes | 5 min | 128 | Reorg detection, recent blocks only |
+//! | Position Pages | 15 s | 8 | Unfiltered first page of `GET /positions`, by page size |
+//! | Chain Reads | per method | 10,000 | Contract views behind `GET /chain/read`, by calldata |
+//!
+//! These are the defaults of [`MemoryCache::new`]. [`MemoryCache::from_settings`]
+//! takes the position, stats and leaderboard TTLs and capacities from
+//! `[cache]`, and [`MemoryCache::reconfigure`] applies new ones while running.
 //!
 //! # Rate Limiting
 //!
@@ -60,15 +66,18 @@
 
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
-use alloy::primitives::B256;
+use alloy::primitives::{Address, B256, Bytes};
 use dashmap::DashMap;
+use moka::Expiry;
 use moka::sync::Cache as MokaCache;
+use parking_lot::RwLock;
 use tracing::debug;
 
+use crate::config::CacheSettings;
 use crate::ports::{Cache, CacheStats};
-use crate::types::entities::{GlobalStats, LeaderboardEntry, LevelStats, Position};
+use crate::types::entities::{GlobalStats, LeaderboardEntry, LevelStats, Page, Position};
 use crate::types::enums::Level;
 use crate::types::primitives::EthAddress;
 
@@ -81,11 +90,9 @@ const POSITION_TTL: Duration = Duration::from_secs(300);
 /// Position cache max capacity.
 const POSITION_MAX_CAPACITY: u64 = 10_000;
 
-/// Global stats cache TTL (1 minute).
-const GLOBAL_STATS_TTL: Duration = Duration::from_secs(60);
+/// Global and level stats cache TTL (1 minute).
+const STATS_TTL: Duration = Duration::from_secs(60);
 
-/// Level stats cache TTL (1 minute).
-const LEVEL_STATS_TTL: Duration = Duration::from_secs(60);
 /// Level stats max capacity (one per level).
 const LEVEL_STATS_MAX_CAPACITY: u64 = 5;
 
@@ -99,6 +106,14 @@ const BLOCK_HASH_TTL: Duration = Duration::from_secs(300);
 /// Block hash max capacity (~15 minutes of blocks at 7s/block).
 const BLOCK_HASH_MAX_CAPACITY: u64 = 128;
 
+/// Position listing cache TTL (15 seconds).
+const POSITION_PAGE_TTL: Duration = Duration::from_secs(15);
+/// Position listing max capacity (different page sizes).
+const POSITION_PAGE_MAX_CAPACITY: u64 = 8;
+
+/// Contract read max capacity (contract, method and arguments).
+const CHAIN_READ_MAX_CAPACITY: u64 = 10_000;
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // MEMORY CACHE
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -117,6 +132,35 @@ const BLOCK_HASH_MAX_CAPACITY: u64 = 128;
 /// via `Arc<MemoryCache>`.
 #[derive(Debug)]
 pub struct MemoryCache {
+    /// Position, stats and leaderboard caches, rebuilt by [`Self::reconfigure`].
+    tiers: RwLock<Tiers>,
+
+    /// Block hash cache for reorg detection.
+    /// Key: block number, Value: block hash.
+    block_hashes: MokaCache<u64, B256>,
+
+    /// First page of all positions by page size, newest first.
+    /// Dropped whenever positions are invalidated.
+    position_pages: MokaCache<u32, Page<Position>>,
+
+    /// Decoded contract reads by contract address and calldata, each kept
+    /// for the TTL of its method.
+    chain_reads: MokaCache<(Address, Bytes), ChainRead>,
+
+    /// Rate limiter: key -> (window_start, count).
+    /// Key format: `{identifier}:{window_start}`.
+    rate_limits: Arc<DashMap<String, (u64, u32)>>,
+
+    /// Cache hit counter.
+    hits: AtomicU64,
+
+    /// Cache miss counter.
+    misses: AtomicU64,
+}
+
+/// The caches whose TTLs and capacities are configurable.
+#[derive(Debug)]
+struct Tiers {
     /// Position cache by user address.
     /// Stores `Option<Position>` to support negative caching (user has no position).
     positions: MokaCache<EthAddress, Option<Position>>,
@@ -129,56 +173,99 @@ pub struct MemoryCache {
 
     /// Leaderboard cache by type name.
     leaderboards: MokaCache<String, Vec<LeaderboardEntry>>,
+}
 
-    /// Block hash cache for reorg detection.
-    /// Key: block number, Value: block hash.
-    block_hashes: MokaCache<u64, B256>,
-
-    /// Rate limiter: key -> (window_start, count).
-    /// Key format: `{identifier}:{window_start}`.
-    rate_limits: Arc<DashMap<String, (u64, u32)>>,
+/// A decoded contract read and how long it is served from cache.
+#[derive(Debug, Clone)]
+struct ChainRead {
+    value: serde_json::Value,
+    ttl: Duration,
+}
 
-    /// Cache hit counter.
-    hits: AtomicU64,
+/// Expires each contract read after its own TTL.
+struct ChainReadExpiry;
+
+impl Expiry<(Address, Bytes), ChainRead> for ChainReadExpiry {
+    fn expire_after_create(
+        &self,
+        _: &(Address, Bytes),
+        read: &ChainRead,
+        _: Instant,
+    ) -> Option<Duration> {
+        Some(read.ttl)
+    }
 
-    /// Cache miss counter.
-    misses: AtomicU64,
+    fn expire_after_update(
+        &self,
+        _: &(Address, Bytes),
+        read: &ChainRead,
+        _: Instant,
+        _: Option<Duration>,
+    ) -> Option<Duration> {
+        Some(read.ttl)
+    }
 }
 
-impl MemoryCache {
-    /// Create a new memory cache with default configuration.
-    #[must_use]
-    pub fn new() -> Self {
+impl Tiers {
+    /// Build the caches; stats TTL applies to global and level stats.
+    fn build(
+        position_ttl: Duration,
+        position_capacity: u64,
+        stats_ttl: Duration,
+        leaderboard_ttl: Duration,
+        leaderboard_capacity: u64,
+    ) -> Self {
         Self {
             positions: MokaCache::builder()
-                .max_capacity(POSITION_MAX_CAPACITY)
-                .time_to_live(POSITION_TTL)
+                .max_capacity(position_capacity)
+                .time_to_live(position_ttl)
                 .build(),
 
             global_stats: MokaCache::builder()
                 .max_capacity(1)
-                .time_to_live(GLOBAL_STATS_TTL)
+                .time_to_live(stats_ttl)
                 .build(),
 
             level_stats: MokaCache::builder()
                 .max_capacity(LEVEL_STATS_MAX_CAPACITY)
-                .time_to_live(LEVEL_STATS_TTL)
+                .time_to_live(stats_ttl)
                 .build(),
 
             leaderboards: MokaCache::builder()
-                .max_capacity(LEADERBOARD_MAX_CAPACITY)
-                .time_to_live(LEADERBOARD_TTL)
-                .build(),
-
-            block_hashes: MokaCache::builder()
-                .max_capacity(BLOCK_HASH_MAX_CAPACITY)
-                .time_to_live(BLOCK_HASH_TTL)
+                .max_capacity(leaderboard_capacity)
+                .time_to_live(leaderboard_ttl)
                 .build(),
+        }
+    }
 
-            rate_limits: Arc::new(DashMap::new()),
-            hits: AtomicU64::new(0),
-            misses: AtomicU64::new(0),
+    /// Copy the entries of `other` into these caches. Their TTLs restart.
+    fn carry_over(&self, other: &Self) {
+        for (address, position) in &other.positions {
+            self.positions.insert(*address, position);
+        }
+        for (_, stats) in &other.global_stats {
+            self.global_stats.insert((), stats);
         }
+        for (level, stats) in &other.level_stats {
+            self.level_stats.insert(*level, stats);
+        }
+        for (name, entries) in &other.leaderboards {
+            self.leaderboards.insert(name.as_ref().clone(), entries);
+        }
+    }
+}
+
+impl MemoryCache {
+    /// Create a new memory cache with default configuration.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_tiers(Tiers::build(
+            POSITION_TTL,
+            POSITION_MAX_CAPACITY,
+            STATS_TTL,
+            LEADERBOARD_TTL,
+            LEADERBOARD_MAX_CAPACITY,
+        ))
     }
 
     /// Create a cache with custom TTLs for testing.
@@ -189,32 +276,82 @@ impl MemoryCache {
     /// * `stats_ttl` - TTL for stats caches
     #[must_use]
     pub fn with_ttls(position_ttl: Duration, stats_ttl: Duration) -> Self {
-        Self {
-            positions: MokaCache::builder()
-                .max_capacity(POSITION_MAX_CAPACITY)
-                .time_to_live(position_ttl)
-                .build(),
+        Self::with_tiers(Tiers::build(
+            position_ttl,
+            POSITION_MAX_CAPACITY,
+            stats_ttl,
+            LEADERBOARD_TTL,
+            LEADERBOARD_MAX_CAPACITY,
+        ))
+    }
 
-            global_stats: MokaCache::builder()
-                .max_capacity(1)
-                .time_to_live(stats_ttl)
-                .build(),
+    /// Create a cache with the TTLs and capacities from `[cache]`.
+    #[must_use]
+    pub fn from_settings(settings: &CacheSettings) -> Self {
+        Self::with_tiers(Self::tiers_for(settings))
+    }
 
-            level_stats: MokaCache::builder()
-                .max_capacity(LEVEL_STATS_MAX_CAPACITY)
-                .time_to_live(stats_ttl)
-                .build(),
+    /// Apply new `[cache]` settings to a running cache.
+    ///
+    /// The position, stats and leaderboard caches are rebuilt with the new
+    /// TTLs and capacities, and their entries carried over. Block hashes,
+    /// contract reads, rate limits and hit counters are not affected.
+    pub fn reconfigure(&self, settings: &CacheSettings) {
+        let rebuilt = Self::tiers_for(settings);
+        let mut tiers = self.tiers.write();
+        rebuilt.carry_over(&tiers);
+        *tiers = rebuilt;
+        drop(tiers);
+        debug!(?settings, "Reconfigured caches");
+    }
 
-            leaderboards: MokaCache::builder()
-                .max_capacity(LEADERBOARD_MAX_CAPACITY)
-                .time_to_live(LEADERBOARD_TTL)
-                .build(),
+    /// TTL of the position cache.
+    #[must_use]
+    pub fn position_ttl(&self) -> Option<Duration> {
+        self.tiers.read().positions.policy().time_to_live()
+    }
+
+    /// TTL of the global and level stats caches.
+    #[must_use]
+    pub fn stats_ttl(&self) -> Option<Duration> {
+        self.tiers.read().global_stats.policy().time_to_live()
+    }
+
+    /// TTL of the leaderboard cache.
+    #[must_use]
+    pub fn leaderboard_ttl(&self) -> Option<Duration> {
+        self.tiers.read().leaderboards.policy().time_to_live()
+    }
+
+    fn tiers_for(settings: &CacheSettings) -> Tiers {
+        Tiers::build(
+            settings.positions_ttl(),
+            settings.positions_max_capacity,
+            settings.stats_ttl(),
+            settings.leaderboard_ttl(),
+            settings.leaderboard_max_capacity,
+        )
+    }
+
+    fn with_tiers(tiers: Tiers) -> Self {
+        Self {
+            tiers: RwLock::new(tiers),
 
             block_hashes: MokaCache::builder()
                 .max_capacity(BLOCK_HASH_MAX_CAPACITY)
                 .time_to_live(BLOCK_HASH_TTL)
                 .build(),
 
+            position_pages: MokaCache::builder()
+                .max_capacity(POSITION_PAGE_MAX_CAPACITY)
+                .time_to_live(POSITION_PAGE_TTL)
+                .build(),
+
+            chain_reads: MokaCache::builder()
+                .max_capacity(CHAIN_READ_MAX_CAPACITY)
+                .expire_after(ChainReadExpiry)
+                .build(),
+
             rate_limits: Arc::new(DashMap::new()),
             hits: AtomicU64::new(0),
             misses: AtomicU64::new(0),
@@ -230,7 +367,7 @@ impl MemoryCache {
     /// Returns `None` on cache miss or TTL expiration.
     #[must_use]
     pub fn get_level_stats(&self, level: Level) -> Option<LevelStats> {
-        let result = self.level_stats.get(&level);
+        let result = self.tiers.read().level_stats.get(&level);
         if result.is_some() {
             self.hits.fetch_add(1, Ordering::Relaxed);
         } else {
@@ -242,19 +379,19 @@ impl MemoryCache {
     /// Cache level stats.
     pub fn set_level_stats(&self, stats: LevelStats) {
         let level = stats.level;
-        self.level_stats.insert(level, stats);
+        self.tiers.read().level_stats.insert(level, stats);
         debug!(?level, "Cached level stats");
     }
 
     /// Invalidate cached level stats.
     pub fn invalidate_level_stats(&self, level: Level) {
-        self.level_stats.invalidate(&level);
+        self.tiers.read().level_stats.invalidate(&level);
         debug!(?level, "Invalidated level stats cache");
     }
 
     /// Invalidate all level stats.
     pub fn invalidate_all_level_stats(&self) {
-        self.level_stats.invalidate_all();
+        self.tiers.read().level_stats.invalidate_all();
         debug!("Invalidated all level stats cache");
     }
 
@@ -269,7 +406,7 @@ impl MemoryCache {
     /// * `leaderboard_type` - Type of leaderboard (e.g., "ghost_streak", "total_extracted")
     #[must_use]
     pub fn get_leaderboard(&self, leaderboard_type: &str) -> Option<Vec<LeaderboardEntry>> {
-        let result = self.leaderboards.get(leaderboard_type);
+        let result = self.tiers.read().leaderboards.get(leaderboard_type);
         if result.is_some() {
             self.hits.fetch_add(1, Ordering::Relaxed);
         } else {
@@ -280,23 +417,77 @@ impl MemoryCache {
 
     /// Cache a leaderboard.
     pub fn set_leaderboard(&self, leaderboard_type: &str, entries: Vec<LeaderboardEntry>) {
-        self.leaderboards
+        self.tiers.read().leaderboards
             .insert(leaderboard_type.to_string(), entries);
         debug!(leaderboard_type, "Cached leaderboard");
     }
 
     /// Invalidate a cached leaderboard.
     pub fn invalidate_leaderboard(&self, leaderboard_type: &str) {
-        self.leaderboards.invalidate(leaderboard_type);
+        self.tiers.read().leaderboards.invalidate(leaderboard_type);
         debug!(leaderboard_type, "Invalidated leaderboard cache");
     }
 
     /// Invalidate all leaderboards.
     pub fn invalidate_all_leaderboards(&self) {
-        self.leaderboards.invalidate_all();
+        self.tiers.read().leaderboards.invalidate_all();
         debug!("Invalidated all leaderboard cache");
     }
 
+    // ═══════════════════════════════════════════════════════════════════════════
+    // POSITION LISTING CACHE (Extended API)
+    // ═══════════════════════════════════════════════════════════════════════════
+
+    /// Get the cached first page of all positions with `limit` entries.
+    #[must_use]
+    pub fn get_positions_page(&self, limit: u32) -> Option<Page<Position>> {
+        let result = self.position_pages.get(&limit);
+        if result.is_some() {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.misses.fetch_add(1, Ordering::Relaxed);
+        }
+        result
+    }
+
+    /// Cache the first page of all positions with `limit` entries.
+    pub fn set_positions_page(&self, limit: u32, page: Page<Position>) {
+        self.position_pages.insert(limit, page);
+        debug!(limit, "Cached positions page");
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════════
+    // CHAIN READ CACHE (Extended API)
+    // ═══════════════════════════════════════════════════════════════════════════
+
+    /// Get the cached result of calling `contract` with `calldata`.
+    #[must_use]
+    pub fn get_chain_read(&self, contract: Address, calldata: &Bytes) -> Option<serde_json::Value> {
+        let result = self
+            .chain_reads
+            .get(&(contract, calldata.clone()))
+            .map(|read| read.value);
+        if result.is_some() {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.misses.fetch_add(1, Ordering::Relaxed);
+        }
+        result
+    }
+
+    /// Cache the result of calling `contract` with `calldata` for `ttl`.
+    pub fn set_chain_read(
+        &self,
+        contract: Address,
+        calldata: Bytes,
+        value: serde_json::Value,
+        ttl: Duration,
+    ) {
+        self.chain_reads
+            .insert((contract, calldata), ChainRead { value, ttl });
+        debug!(%contract, ?ttl, "Cached chain read");
+    }
+
     // ═══════════════════════════════════════════════════════════════════════════
     // BLOCK HASH CACHE (Extended API for reorg detection)
     // ═══════════════════════════════════════════════════════════════════════════
@@ -393,11 +584,12 @@ impl MemoryCache {
     /// Moka performs maintenance lazily; this forces it to run immediately.
     /// Useful for tests or before taking memory measurements.
     pub fn run_pending_tasks(&self) {
-        self.positions.run_pending_tasks();
-        self.global_stats.run_pending_tasks();
-        self.level_stats.run_pending_tasks();
-        self.leaderboards.run_pending_tasks();
+        self.tiers.read().positions.run_pending_tasks();
+        self.tiers.read().global_stats.run_pending_tasks();
+        self.tiers.read().level_stats.run_pending_tasks();
+        self.tiers.read().leaderboards.run_pending_tasks();
         self.block_hashes.run_pending_tasks();
+        self.chain_reads.run_pending_tasks();
     }
 }
 
@@ -413,7 +605,8 @@ impl Default for MemoryCache {
 
 impl Cache for MemoryCache {
     fn get_position(&self, address: &EthAddress) -> Option<Position> {
-        match self.positions.get(address) {
+        let cached = self.tiers.read().positions.get(address);
+        match cached {
             Some(Some(pos)) => {
                 self.hits.fetch_add(1, Ordering::Relaxed);
                 Some(pos)
@@ -432,17 +625,19 @@ impl Cache for MemoryCache {
     }
 
     fn set_position(&self, address: &EthAddress, position: Option<Position>) {
-        self.positions.insert(*address, position);
+        self.tiers.read().positions.insert(*address, position);
         debug!(%address, "Cached position");
     }
 
     fn invalidate_position(&self, address: &EthAddress) {
-        self.positions.invalidate(address);
+        self.tiers.read().positions.invalidate(address);
+        self.position_pages.invalidate_all();
         debug!(%address, "Invalidated position cache");
     }
 
     fn invalidate_all_positions(&self) {
-        self.positions.invalidate_all();
+        self.tiers.read().positions.invalidate_all();
+        self.position_pages.invalidate_all();
         debug!("Invalidated all position cache");
     }
 
@@ -456,6 +651,8 @@ impl Cache for MemoryCache {
         // Note: moka's iter() returns Arc<K>, so we clone the inner value
         // for use with invalidate().
         let keys_to_remove: Vec<_> = self
+            .tiers
+            .read()
             .positions
             .iter()
             .filter(|(_, opt_pos)| opt_pos.as_ref().is_some_and(|p| p.level == *level))
@@ -463,8 +660,9 @@ impl Cache for MemoryCache {
             .collect();
 
         for key in &keys_to_remove {
-            self.positions.invalidate(key);
+            self.tiers.read().positions.invalidate(key);
         }
+        self.position_pages.invalidate_all();
 
         // Also invalidate level stats
         self.invalidate_level_stats(*level);
@@ -477,7 +675,7 @@ impl Cache for MemoryCache {
     }
 
     fn get_global_stats(&self) -> Option<GlobalStats> {
-        let result = self.global_stats.get(&());
+        let result = self.tiers.read().global_stats.get(&());
         if result.is_some() {
             self.hits.fetch_add(1, Ordering::Relaxed);
         } else {
@@ -487,7 +685,7 @@ impl Cache for MemoryCache {
     }
 
     fn set_global_stats(&self, stats: GlobalStats) {
-        self.global_stats.insert((), stats);
+        self.tiers.read().global_stats.insert((), stats);
         debug!("Cached global stats");
     }
 
@@ -528,11 +726,13 @@ impl Cache for MemoryCache {
     }
 
     fn clear_all(&self) {
-        self.positions.invalidate_all();
-        self.global_stats.invalidate_all();
-        self.level_stats.invalidate_all();
-        self.leaderboards.invalidate_all();
+        self.tiers.read().positions.invalidate_all();
+        self.tiers.read().global_stats.invalidate_all();
+        self.tiers.read().level_stats.invalidate_all();
+        self.tiers.read().leaderboards.invalidate_all();
         self.block_hashes.invalidate_all();
+        self.position_pages.invalidate_all();
+        self.chain_reads.invalidate_all();
         self.rate_limits.clear();
 
         // Reset counters
@@ -546,13 +746,13 @@ impl Cache for MemoryCache {
         // entry_count() returns u64; truncation to usize is fine since
         // the cache has a max capacity of 10K entries.
         #[allow(clippy::cast_possible_truncation)]
-        let position_count = self.positions.entry_count() as usize;
+        let position_count = self.tiers.read().positions.entry_count() as usize;
 
         CacheStats {
             hits: self.hits.load(Ordering::Relaxed),
             misses: self.misses.load(Ordering::Relaxed),
             position_count,
-            has_global_stats: self.global_stats.get(&()).is_some(),
+            has_global_stats: self.tiers.read().global_stats.get(&()).is_some(),
         }
     }
 }
@@ -565,8 +765,7 @@ impl Cache for MemoryCache {
 fn current_timestamp() -> u64 {
     std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
-        .map(|d| d.as_secs())
-        .unwrap_or(0)
+        .map_or(0, |d| d.as_secs())
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -595,6 +794,7 @@ mod tests {
             user_address: address,
             level: Level::Darknet,
             amount: TokenAmount::from_wei(U256::from(1_000_000_000_000_000_000u128), 18),
+            effective_stake: TokenAmount::from_wei(U256::from(1_000_000_000_000_000_000u128), 18),
             reward_debt: TokenAmount::zero(),
             entry_timestamp: Utc::now(),
             last_add_timestamp: None,
@@ -603,6 +803,8 @@ mod tests {
             is_extracted: false,
             exit_reason: None,
             exit_timestamp: None,
+            survival_seconds: None,
+            scans_survived: None,
             extracted_amount: None,
             extracted_rewards: None,
             created_at_block: crate::types::primitives::BlockNumber::new(100),
@@ -628,12 +830,18 @@ mod tests {
         LevelStats {
             level,
             total_staked: TokenAmount::from_wei(U256::from(500_000u128), 18),
+            total_effective_staked: TokenAmount::from_wei(U256::from(600_000u128), 18),
             alive_count: 50,
             total_deaths: 25,
+            deaths_24h: 3,
             total_extracted: 30,
+            total_culled: 5,
             total_burned: TokenAmount::from_wei(U256::from(100_000u128), 18),
             total_distributed: TokenAmount::from_wei(U256::from(200_000u128), 18),
             highest_ghost_streak: GhostStreak::new(10).unwrap(),
+            total_ghost_streak: 150,
+            total_survival_seconds: 360_000,
+            survival_samples: 60,
             updated_at: Utc::now(),
         }
     }
@@ -646,7 +854,7 @@ mod tests {
     fn position_cache_hit() {
         let cache = MemoryCache::new();
         let addr = sample_address();
-        let pos = sample_position(addr.clone());
+        let pos = sample_position(addr);
 
         cache.set_position(&addr, Some(pos.clone()));
 
@@ -693,7 +901,7 @@ mod tests {
     fn position_invalidate() {
         let cache = MemoryCache::new();
         let addr = sample_address();
-        let pos = sample_position(addr.clone());
+        let pos = sample_position(addr);
 
         cache.set_position(&addr, Some(pos));
         cache.invalidate_position(&addr);
@@ -712,8 +920,8 @@ mod tests {
         let addr1 = sample_address();
         let addr2 = EthAddress::from_hex("0xabcdef0123456789abcdef0123456789abcdef01").unwrap();
 
-        cache.set_position(&addr1, Some(sample_position(addr1.clone())));
-        cache.set_position(&addr2, Some(sample_position(addr2.clone())));
+        cache.set_position(&addr1, Some(sample_position(addr1)));
+        cache.set_position(&addr2, Some(sample_position(addr2)));
 
         // Moka batches internal operations; run pending tasks to sync
         cache.run_pending_tasks();
@@ -732,10 +940,10 @@ mod tests {
         let addr1 = sample_address();
         let addr2 = EthAddress::from_hex("0xabcdef0123456789abcdef0123456789abcdef01").unwrap();
 
-        let mut pos1 = sample_position(addr1.clone());
+        let mut pos1 = sample_position(addr1);
         pos1.level = Level::Darknet;
 
-        let mut pos2 = sample_position(addr2.clone());
+        let mut pos2 = sample_position(addr2);
         pos2.level = Level::Subnet;
 
         cache.set_position(&addr1, Some(pos1));
@@ -751,6 +959,28 @@ mod tests {
         assert!(cache.get_position(&addr2).is_some());
     }
 
+    #[test]
+    fn positions_page_dropped_with_positions() {
+        let cache = MemoryCache::new();
+        let addr = sample_address();
+        let page = Page {
+            items: vec![sample_position(addr)],
+            next_cursor: None,
+        };
+
+        cache.set_positions_page(50, page.clone());
+        assert_eq!(cache.get_positions_page(50), Some(page.clone()));
+        assert_eq!(cache.get_positions_page(10), None);
+
+        // Any position change may reorder or refill the first page
+        cache.invalidate_position(&addr);
+        assert_eq!(cache.get_positions_page(50), None);
+
+        cache.set_positions_page(50, page);
+        cache.invalidate_level(&Level::Vault);
+        assert_eq!(cache.get_positions_page(50), None);
+    }
+
     // ═══════════════════════════════════════════════════════════════════════════
     // GLOBAL STATS CACHE TESTS
     // ═══════════════════════════════════════════════════════════════════════════
@@ -792,7 +1022,7 @@ mod tests {
         let cache = MemoryCache::new();
         let stats = sample_level_stats(Level::Darknet);
 
-        cache.set_level_stats(stats.clone());
+        cache.set_level_stats(stats);
 
         let result = cache.get_level_stats(Level::Darknet);
         assert!(result.is_some());
@@ -840,7 +1070,7 @@ mod tests {
             },
         ];
 
-        cache.set_leaderboard("ghost_streak", entries.clone());
+        cache.set_leaderboard("ghost_streak", entries);
 
         let result = cache.get_leaderboard("ghost_streak");
         assert!(result.is_some());
@@ -890,6 +1120,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::cast_possible_truncation)]
     fn invalidate_blocks_from() {
         let cache = MemoryCache::new();
 
@@ -1014,7 +1245,7 @@ mod tests {
         let cache = MemoryCache::with_ttls(Duration::from_millis(100), Duration::from_secs(60));
 
         let addr = sample_address();
-        cache.set_position(&addr, Some(sample_position(addr.clone())));
+        cache.set_position(&addr, Some(sample_position(addr)));
 
         // Should exist immediately
         assert!(cache.get_position(&addr).is_some());
@@ -1027,6 +1258,73 @@ mod tests {
         assert!(cache.get_position(&addr).is_none());
     }
 
+    #[test]
+    fn reconfigure_applies_new_ttls_and_keeps_entries() {
+        let settings = CacheSettings {
+            positions_ttl_ms: 60_000,
+            positions_max_capacity: 100,
+            leaderboard_ttl_ms: 60_000,
+            leaderboard_max_capacity: 10,
+            stats_ttl_ms: 30_000,
+        };
+        let cache = MemoryCache::from_settings(&settings);
+        assert_eq!(cache.position_ttl(), Some(Duration::from_secs(60)));
+        assert_eq!(cache.stats_ttl(), Some(Duration::from_secs(30)));
+
+        let addr = sample_address();
+        cache.set_position(&addr, Some(sample_position(addr)));
+        cache.set_global_stats(sample_global_stats());
+
+        cache.reconfigure(&CacheSettings {
+            positions_ttl_ms: 100,
+            ..settings
+        });
+        assert_eq!(cache.position_ttl(), Some(Duration::from_millis(100)));
+        assert_eq!(cache.leaderboard_ttl(), Some(Duration::from_secs(60)));
+
+        // Entries survive the rebuild, then expire on the new TTL
+        assert!(cache.get_position(&addr).is_some());
+        assert!(cache.get_global_stats().is_some());
+        sleep(Duration::from_millis(150));
+        cache.run_pending_tasks();
+        assert!(cache.get_position(&addr).is_none());
+        assert!(cache.get_global_stats().is_some());
+    }
+
+    #[test]
+    fn chain_reads_expire_per_method() {
+        let cache = MemoryCache::new();
+        let contract = Address::repeat_byte(0xc0);
+        let fast = Bytes::from_static(&[1, 2, 3, 4]);
+        let slow = Bytes::from_static(&[5, 6, 7, 8]);
+
+        cache.set_chain_read(
+            contract,
+            fast.clone(),
+            serde_json::json!("1"),
+            Duration::from_millis(100),
+        );
+        cache.set_chain_read(
+            contract,
+            slow.clone(),
+            serde_json::json!(true),
+            Duration::from_secs(60),
+        );
+        assert_eq!(
+            cache.get_chain_read(contract, &fast),
+            Some(serde_json::json!("1"))
+        );
+        assert!(cache.get_chain_read(Address::ZERO, &fast).is_none());
+
+        sleep(Duration::from_millis(150));
+        cache.run_pending_tasks();
+        assert!(cache.get_chain_read(contract, &fast).is_none());
+        assert_eq!(
+            cache.get_chain_read(contract, &slow),
+            Some(serde_json::json!(true))
+        );
+    }
+
     // ═══════════════════════════════════════════════════════════════════════════
     // HIT RATE TESTS
     // ═══════════════════════════════════════════════════════════════════════════
@@ -1036,7 +1334,7 @@ mod tests {
         let cache = MemoryCache::new();
         let addr = sample_address();
 
-        cache.set_position(&addr, Some(sample_position(addr.clone())));
+        cache.set_position(&addr, Some(sample_position(addr)));
 
         // 3 hits
         cache.get_position(&addr);
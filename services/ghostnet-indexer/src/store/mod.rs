@@ -60,16 +60,32 @@
 //! let position = store.get_active_position(&address).await?;
 //! ```
 //!
+//! # Write Batching
+//!
+//! [`PostgresStore::batched`] returns a store whose writes the pipeline
+//! commits one block or batch window at a time, together with the
+//! checkpoint. Handlers call the same port methods either way.
+//!
+//! ```ignore
+//! let writer = store.batched();
+//! writer.save_position(&position).await?;
+//! writer.set_last_block(block, hash).await?;
+//! writer.commit_batch().await?;
+//! ```
+//!
 //! # Migrations
 //!
 //! Migrations are located in `migrations/` and run via `sqlx migrate run`.
 //! See individual migration files for schema details.
 
+mod batch;
 mod cache;
 mod postgres;
+pub mod retry;
 
 pub use cache::MemoryCache;
-pub use postgres::PostgresStore;
+pub use postgres::{PoolHealth, PostgresStore, StoreHealth};
+pub use retry::RetryPolicy;
 
 // Re-export commonly used types for convenience
 pub use sqlx::postgres::PgPool;
@@ -15,44 +15,129 @@
     clippy::cast_sign_loss,
     clippy::cast_possible_wrap,
     clippy::cast_lossless, // Using `as i64` for u32 is clear in DB binding context
-    clippy::use_self       // TryFrom implementations read better with explicit type names
+    clippy::use_self,      // TryFrom implementations read better with explicit type names
+    clippy::significant_drop_tightening // Misses that committing a `Conn` consumes it
 )]
 
-use alloy::primitives::B256;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Instant;
+
+use alloy::primitives::{Address, B256, U256};
 use async_trait::async_trait;
-use sqlx::{FromRow, postgres::PgPool};
+use serde::Serialize;
+use sqlx::{
+    FromRow, PgExecutor, QueryBuilder,
+    postgres::{PgConnectOptions, PgConnection, PgPool, Postgres},
+};
 use tracing::{debug, instrument};
 use uuid::Uuid;
 
 use crate::error::{InfraError, Result};
+use crate::indexer::Contract;
+use crate::obs;
 use crate::ports::{
-    DeathStore, IndexerStateStore, MarketStore, PositionStore, ScanStore, StatsStore,
+    BoostStore, DeathStore, EventLogStore, EventOutboxStore, HolderStore, IdentifiedMessage,
+    IndexerStateStore, LeaderboardStore, MarketStore, PositionStore, QuarantineStore, RawLogStore,
+    ScanStore, StatsStore, TokenFlowStore,
 };
 use crate::types::entities::{
-    Bet, Death, GlobalStats, LevelStats, LevelStatsDelta, Position, PositionHistoryEntry, Round,
-    Scan, ScanFinalizationData,
+    AddressFlows, Bet, Boost, BurnRate, CascadeEarnings, CascadeShare, Death, EffectiveStakeChange,
+    EventLogBounds, ExitStreakCount, GlobalStats, GlobalStatsDelta, HistoryBucket, HistoryCursor,
+    HolderBalance, HolderSort, LapsedBoost, LeaderboardEntry, LevelHistoryPoint, LevelScanStats,
+    LevelStats, LevelStatsDelta, LevelSurvival, LoggedEvent, OutboxEvent, OutboxRecord, Page,
+    Pools, Position, PositionCursor, PositionFilter, PositionHistoryEntry, QuarantinedEvent,
+    RawLog, Round, RoundSettlement, Scan, ScanCascadeEarnings, ScanFinalizationData,
+    TokenFlowDelta, TokenTransfer,
 };
-use crate::types::enums::Level;
+use crate::types::enums::{BoostType, LeaderboardType, Level, RoundType};
 use crate::types::primitives::{BlockNumber, EthAddress, GhostStreak, TokenAmount};
 
+use super::batch::{self, Conn, WriteBatch};
+use super::retry::RetryPolicy;
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // POSTGRES STORE
 // ═══════════════════════════════════════════════════════════════════════════════
 
+/// Schema of the tables of the chain indexed on its own.
+const PUBLIC_SCHEMA: &str = "public";
+
+/// Stands for the schema in [`DROPPED_BY_MIGRATIONS`].
+const SCHEMA_PLACEHOLDER: &str = "__SCHEMA__";
+
+/// Stand-ins for what the schema v2 migration drops before creating its
+/// tables, created in a new schema; see
+/// [`run_schema_migrations`](PostgresStore::run_schema_migrations).
+const DROPPED_BY_MIGRATIONS: &[&str] = &[
+    "CREATE MATERIALIZED VIEW __SCHEMA__.death_stats_hourly AS SELECT 1",
+    "CREATE MATERIALIZED VIEW __SCHEMA__.position_activity_hourly AS SELECT 1",
+    "CREATE TABLE __SCHEMA__.deaths ()",
+    "CREATE TABLE __SCHEMA__.position_history ()",
+    "CREATE TABLE __SCHEMA__.positions ()",
+    "CREATE TABLE __SCHEMA__.scans ()",
+    "CREATE TABLE __SCHEMA__.level_stats ()",
+    "CREATE TABLE __SCHEMA__.global_stats ()",
+    "CREATE TABLE __SCHEMA__.block_hashes ()",
+    "CREATE TABLE __SCHEMA__.block_history ()",
+    "CREATE TABLE __SCHEMA__.indexer_state ()",
+    "CREATE FUNCTION __SCHEMA__.handle_reorg() RETURNS void LANGUAGE sql AS ''",
+];
+
 /// PostgreSQL-based store implementation.
 ///
 /// Implements all store port traits using SQLx for database access.
 /// Uses TimescaleDB hypertables for efficient time-series data storage.
+///
+/// Writes are committed as they are made, unless the store is
+/// [batched](Self::batched).
 #[derive(Debug, Clone)]
 pub struct PostgresStore {
     pool: PgPool,
+    /// Transaction all statements run in, for batched stores. Shared by
+    /// clones.
+    batch: Option<Arc<WriteBatch>>,
+    /// Retries of connection acquisition; see the `retry` module.
+    retry: RetryPolicy,
 }
 
 impl PostgresStore {
     /// Create a new PostgreSQL store with the given connection pool.
     #[must_use]
     pub const fn new(pool: PgPool) -> Self {
-        Self { pool }
+        Self {
+            pool,
+            batch: None,
+            retry: RetryPolicy::DEFAULT,
+        }
+    }
+
+    /// Retry acquiring connections and beginning transactions by `policy`.
+    #[must_use]
+    pub const fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry = policy;
+        self
+    }
+
+    /// A store on the same pool that batches its writes.
+    ///
+    /// Its statements all run in one transaction, committed by
+    /// [`commit_batch`](IndexerStateStore::commit_batch), with the rows of
+    /// the high-volume tables inserted in bulk; see the `batch` module. The
+    /// store and its clones share the batch, so give each pipeline its own.
+    #[must_use]
+    pub fn batched(&self) -> Self {
+        Self {
+            pool: self.pool.clone(),
+            batch: Some(Arc::default()),
+            retry: self.retry,
+        }
+    }
+
+    /// Check if the store batches its writes.
+    #[must_use]
+    pub const fn is_batched(&self) -> bool {
+        self.batch.is_some()
     }
 
     /// Get a reference to the underlying connection pool.
@@ -73,6 +158,217 @@ impl PostgresStore {
             .map_err(|e| InfraError::Internal(format!("Migration error: {e}")))?;
         Ok(())
     }
+
+    /// Scope connections made with `options` to the tables of one chain.
+    ///
+    /// Unqualified tables resolve to `schema`, with `public` after it for the
+    /// TimescaleDB functions, and migrations default the `chain_id` columns
+    /// to `chain_id`.
+    #[must_use]
+    pub fn chain_options(
+        options: PgConnectOptions,
+        schema: &str,
+        chain_id: u64,
+    ) -> PgConnectOptions {
+        let search_path = if schema == PUBLIC_SCHEMA {
+            PUBLIC_SCHEMA.to_string()
+        } else {
+            format!("{schema},{PUBLIC_SCHEMA}")
+        };
+        options.options([
+            ("search_path", search_path),
+            ("ghostnet.chain_id", chain_id.to_string()),
+        ])
+    }
+
+    /// Create `schema` if needed and run pending migrations in it.
+    ///
+    /// The store's connections must be [scoped](Self::chain_options) to
+    /// `schema`. TimescaleDB is installed in `public`, where every schema
+    /// finds it. The first migrations drop tables of an older design if they
+    /// exist; in a new schema those names would resolve to the tables in
+    /// `public`, so empty stand-ins are created for them to drop instead.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the schema cannot be created or migrations fail.
+    pub async fn run_schema_migrations(&self, schema: &str) -> Result<()> {
+        if schema != PUBLIC_SCHEMA {
+            sqlx::query("CREATE EXTENSION IF NOT EXISTS timescaledb SCHEMA public CASCADE")
+                .execute(&self.pool)
+                .await
+                .map_err(InfraError::Database)?;
+            let migrated: Option<String> = sqlx::query_scalar("SELECT to_regclass($1)::text")
+                .bind(format!("{schema}._sqlx_migrations"))
+                .fetch_one(&self.pool)
+                .await
+                .map_err(InfraError::Database)?;
+            if migrated.is_none() {
+                let mut tx = self.pool.begin().await.map_err(InfraError::Database)?;
+                sqlx::query(&format!("CREATE SCHEMA IF NOT EXISTS {schema}"))
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(InfraError::Database)?;
+                for statement in DROPPED_BY_MIGRATIONS {
+                    sqlx::query(&statement.replace(SCHEMA_PLACEHOLDER, schema))
+                        .execute(&mut *tx)
+                        .await
+                        .map_err(InfraError::Database)?;
+                }
+                tx.commit().await.map_err(InfraError::Database)?;
+            }
+        }
+        self.run_migrations().await
+    }
+
+    /// Connection for a statement not touching the bulk tables: the batch's
+    /// transaction for batched stores, a pooled connection otherwise.
+    async fn conn(&self) -> Result<Conn<'_>> {
+        match &self.batch {
+            Some(batch) => batch.conn(&self.pool, &self.retry, false).await,
+            None => Ok(Conn::Pooled(self.retry.acquire(&self.pool).await?)),
+        }
+    }
+
+    /// Connection for a statement reading or updating the bulk tables, with
+    /// the batch's buffered rows inserted first.
+    async fn flushed_conn(&self) -> Result<Conn<'_>> {
+        match &self.batch {
+            Some(batch) => batch.conn(&self.pool, &self.retry, true).await,
+            None => self.conn().await,
+        }
+    }
+
+    /// Connection for statements that must apply together, committed with
+    /// [`Conn::commit`]: a transaction of their own, or the batch's.
+    async fn transaction(&self) -> Result<Conn<'_>> {
+        match &self.batch {
+            Some(batch) => batch.conn(&self.pool, &self.retry, false).await,
+            None => Ok(Conn::Transaction(self.retry.begin(&self.pool).await?)),
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// HEALTH
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Connectivity and replication state of the database, for `GET /health`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StoreHealth {
+    /// Whether the database answered.
+    pub reachable: bool,
+    /// Round trip of the health query, in milliseconds.
+    pub latency_ms: Option<u64>,
+    /// Whether the server is a standby replaying WAL, which rejects writes.
+    pub in_recovery: Option<bool>,
+    /// Seconds since the last transaction a standby replayed.
+    pub replay_lag_secs: Option<f64>,
+    /// Server's `statement_timeout` for this session (`0` is unlimited).
+    pub statement_timeout: Option<String>,
+    /// Server's `synchronous_commit` setting.
+    pub synchronous_commit: Option<String>,
+    /// Connection pool usage.
+    pub pool: PoolHealth,
+    /// Why the database could not be reached.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl StoreHealth {
+    /// Check if the indexer can write: the database answered and is not a
+    /// read-only standby.
+    #[must_use]
+    pub fn is_healthy(&self) -> bool {
+        self.reachable && self.in_recovery != Some(true)
+    }
+}
+
+/// Connection pool usage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct PoolHealth {
+    /// Open connections.
+    pub size: u32,
+    /// Open connections not in use.
+    pub idle: u32,
+    /// Most connections the pool opens.
+    pub max: u32,
+}
+
+impl PoolHealth {
+    /// Share of the pool's capacity in use, from 0 to 1.
+    #[must_use]
+    pub fn utilization(&self) -> f64 {
+        if self.max == 0 {
+            return 0.0;
+        }
+        f64::from(self.size.saturating_sub(self.idle)) / f64::from(self.max)
+    }
+}
+
+/// Row of the health query.
+#[derive(Debug, FromRow)]
+struct HealthRow {
+    in_recovery: bool,
+    replay_lag_secs: Option<f64>,
+    statement_timeout: String,
+    synchronous_commit: String,
+}
+
+impl PostgresStore {
+    /// Usage of the connection pool.
+    #[must_use]
+    pub fn pool_health(&self) -> PoolHealth {
+        PoolHealth {
+            size: self.pool.size(),
+            idle: self.pool.num_idle() as u32,
+            max: self.pool.options().get_max_connections(),
+        }
+    }
+
+    /// Check that the database answers, and whether it is a standby and how
+    /// far it lags.
+    ///
+    /// Never fails: an unreachable database is reported as such.
+    #[instrument(skip(self))]
+    pub async fn health(&self) -> StoreHealth {
+        let _timer = obs::store_timer("health");
+        let started = Instant::now();
+        let row = sqlx::query_as::<_, HealthRow>(
+            "SELECT pg_is_in_recovery() AS in_recovery, \
+             EXTRACT(EPOCH FROM now() - pg_last_xact_replay_timestamp())::float8 \
+                 AS replay_lag_secs, \
+             current_setting('statement_timeout') AS statement_timeout, \
+             current_setting('synchronous_commit') AS synchronous_commit",
+        )
+        .fetch_one(&self.pool)
+        .await;
+        let latency_ms = started.elapsed().as_millis() as u64;
+        let pool = self.pool_health();
+
+        match row {
+            Ok(row) => StoreHealth {
+                reachable: true,
+                latency_ms: Some(latency_ms),
+                in_recovery: Some(row.in_recovery),
+                replay_lag_secs: row.replay_lag_secs.filter(|_| row.in_recovery),
+                statement_timeout: Some(row.statement_timeout),
+                synchronous_commit: Some(row.synchronous_commit),
+                pool,
+                error: None,
+            },
+            Err(e) => StoreHealth {
+                reachable: false,
+                latency_ms: None,
+                in_recovery: None,
+                replay_lag_secs: None,
+                statement_timeout: None,
+                synchronous_commit: None,
+                pool,
+                error: Some(e.to_string()),
+            },
+        }
+    }
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -86,6 +382,7 @@ struct PositionRow {
     user_address: Vec<u8>,
     level: i16,
     amount: sqlx::types::BigDecimal,
+    effective_stake: sqlx::types::BigDecimal,
     reward_debt: sqlx::types::BigDecimal,
     entry_timestamp: chrono::DateTime<chrono::Utc>,
     last_add_timestamp: Option<chrono::DateTime<chrono::Utc>>,
@@ -94,6 +391,8 @@ struct PositionRow {
     is_extracted: bool,
     exit_reason: Option<String>,
     exit_timestamp: Option<chrono::DateTime<chrono::Utc>>,
+    survival_seconds: Option<i64>,
+    scans_survived: Option<i32>,
     extracted_amount: Option<sqlx::types::BigDecimal>,
     extracted_rewards: Option<sqlx::types::BigDecimal>,
     created_at_block: i64,
@@ -114,6 +413,7 @@ impl TryFrom<PositionRow> for Position {
             level: Level::try_from(row.level as u8)
                 .map_err(|e| InfraError::Internal(format!("Invalid level in DB: {e}")))?,
             amount: TokenAmount::from_bigdecimal(&row.amount),
+            effective_stake: TokenAmount::from_bigdecimal(&row.effective_stake),
             reward_debt: TokenAmount::from_bigdecimal(&row.reward_debt),
             entry_timestamp: row.entry_timestamp,
             last_add_timestamp: row.last_add_timestamp,
@@ -127,6 +427,8 @@ impl TryFrom<PositionRow> for Position {
                 .transpose()
                 .map_err(|e| InfraError::Internal(format!("Invalid exit reason in DB: {e}")))?,
             exit_timestamp: row.exit_timestamp,
+            survival_seconds: row.survival_seconds,
+            scans_survived: row.scans_survived.map(|s| s.max(0) as u32),
             extracted_amount: row
                 .extracted_amount
                 .map(|d| TokenAmount::from_bigdecimal(&d)),
@@ -139,16 +441,172 @@ impl TryFrom<PositionRow> for Position {
     }
 }
 
+/// Database row for position history entries.
+#[derive(Debug, FromRow)]
+struct PositionHistoryRow {
+    id: Uuid,
+    position_id: Uuid,
+    user_address: Vec<u8>,
+    action: String,
+    amount_change: sqlx::types::BigDecimal,
+    new_total: sqlx::types::BigDecimal,
+    level: Option<i16>,
+    ghost_streak: Option<i32>,
+    block_number: i64,
+    timestamp: chrono::DateTime<chrono::Utc>,
+}
+
+impl TryFrom<PositionHistoryRow> for PositionHistoryEntry {
+    type Error = InfraError;
+
+    fn try_from(row: PositionHistoryRow) -> std::result::Result<Self, Self::Error> {
+        // Both are backfilled by the migration that added them
+        let level = row
+            .level
+            .ok_or_else(|| InfraError::Internal("Missing history level in DB".into()))?;
+        let ghost_streak = row
+            .ghost_streak
+            .ok_or_else(|| InfraError::Internal("Missing history ghost streak in DB".into()))?;
+
+        Ok(PositionHistoryEntry {
+            id: row.id,
+            position_id: row.position_id,
+            user_address: EthAddress::new(
+                row.user_address
+                    .try_into()
+                    .map_err(|_| InfraError::Internal("Invalid address length in DB".into()))?,
+            ),
+            action: row
+                .action
+                .parse()
+                .map_err(|e| InfraError::Internal(format!("Invalid history action in DB: {e}")))?,
+            amount_change: TokenAmount::from_bigdecimal(&row.amount_change),
+            new_total: TokenAmount::from_bigdecimal(&row.new_total),
+            level: Level::try_from(level as u8)
+                .map_err(|e| InfraError::Internal(format!("Invalid level in DB: {e}")))?,
+            ghost_streak: GhostStreak::new(ghost_streak)
+                .map_err(|e| InfraError::Internal(format!("Invalid ghost streak in DB: {e}")))?,
+            block_number: BlockNumber::new(row.block_number as u64),
+            timestamp: row.timestamp,
+        })
+    }
+}
+
+/// Insert or update a position row.
+async fn upsert_position<'e, E: PgExecutor<'e>>(executor: E, position: &Position) -> Result<()> {
+    // Positions are now a regular table with simple UUID primary key.
+    // This simplifies upserts since we don't need composite keys.
+    // Note: updated_at_block uses created_at_block for now (entity doesn't track it separately)
+    sqlx::query(
+        r#"
+        INSERT INTO positions (
+            id, user_address, level, amount, reward_debt, entry_timestamp,
+            last_add_timestamp, ghost_streak, is_alive, is_extracted,
+            exit_reason, exit_timestamp, extracted_amount, extracted_rewards,
+            created_at_block, updated_at_block, updated_at, survival_seconds, scans_survived,
+            effective_stake
+        )
+        VALUES (
+            $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $15, $16, $17, $18,
+            $19
+        )
+        ON CONFLICT (id) DO UPDATE SET
+            amount = EXCLUDED.amount,
+            effective_stake = EXCLUDED.effective_stake,
+            reward_debt = EXCLUDED.reward_debt,
+            last_add_timestamp = EXCLUDED.last_add_timestamp,
+            ghost_streak = EXCLUDED.ghost_streak,
+            is_alive = EXCLUDED.is_alive,
+            is_extracted = EXCLUDED.is_extracted,
+            exit_reason = EXCLUDED.exit_reason,
+            exit_timestamp = EXCLUDED.exit_timestamp,
+            extracted_amount = EXCLUDED.extracted_amount,
+            extracted_rewards = EXCLUDED.extracted_rewards,
+            updated_at_block = $15,
+            updated_at = EXCLUDED.updated_at,
+            survival_seconds = EXCLUDED.survival_seconds,
+            scans_survived = EXCLUDED.scans_survived
+        "#,
+    )
+    .bind(position.id)
+    .bind(position.user_address.as_bytes())
+    .bind(position.level as i16)
+    .bind(position.amount.to_bigdecimal())
+    .bind(position.reward_debt.to_bigdecimal())
+    .bind(position.entry_timestamp)
+    .bind(position.last_add_timestamp)
+    .bind(position.ghost_streak.value())
+    .bind(position.is_alive)
+    .bind(position.is_extracted)
+    .bind(position.exit_reason.map(|r| r.to_string()))
+    .bind(position.exit_timestamp)
+    .bind(
+        position
+            .extracted_amount
+            .as_ref()
+            .map(TokenAmount::to_bigdecimal),
+    )
+    .bind(
+        position
+            .extracted_rewards
+            .as_ref()
+            .map(TokenAmount::to_bigdecimal),
+    )
+    .bind(position.created_at_block.value() as i64)
+    .bind(position.updated_at)
+    .bind(position.survival_seconds)
+    .bind(position.scans_survived.map(|s| s.min(i32::MAX as u32) as i32))
+    .bind(position.effective_stake.to_bigdecimal())
+    .execute(executor)
+    .await
+    .map_err(InfraError::Database)?;
+
+    Ok(())
+}
+
+/// Insert a position history row.
+async fn insert_history<'e, E: PgExecutor<'e>>(
+    executor: E,
+    entry: &PositionHistoryEntry,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO position_history (
+            id, position_id, user_address, action, amount_change, new_total,
+            level, ghost_streak, block_number, timestamp
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+        "#,
+    )
+    .bind(entry.id)
+    .bind(entry.position_id)
+    .bind(entry.user_address.as_bytes())
+    .bind(entry.action.name())
+    .bind(entry.amount_change.to_bigdecimal())
+    .bind(entry.new_total.to_bigdecimal())
+    .bind(entry.level as i16)
+    .bind(entry.ghost_streak.value())
+    .bind(entry.block_number.value() as i64)
+    .bind(entry.timestamp)
+    .execute(executor)
+    .await
+    .map_err(InfraError::Database)?;
+
+    Ok(())
+}
+
 #[async_trait]
 impl PositionStore for PostgresStore {
     #[instrument(skip(self), fields(address = %address))]
     async fn get_active_position(&self, address: &EthAddress) -> Result<Option<Position>> {
+        let _timer = obs::store_timer("get_active_position");
         let row = sqlx::query_as::<_, PositionRow>(
             r#"
             SELECT id, user_address, level, amount, reward_debt, entry_timestamp,
                    last_add_timestamp, ghost_streak, is_alive, is_extracted,
                    exit_reason, exit_timestamp, extracted_amount, extracted_rewards,
-                   created_at_block, updated_at
+                   created_at_block, updated_at, survival_seconds, scans_survived,
+                   effective_stake
             FROM positions
             WHERE user_address = $1 AND is_alive = true AND is_extracted = false
             ORDER BY entry_timestamp DESC
@@ -156,7 +614,7 @@ impl PositionStore for PostgresStore {
             "#,
         )
         .bind(address.as_bytes())
-        .fetch_optional(&self.pool)
+        .fetch_optional(&mut *self.conn().await?)
         .await
         .map_err(InfraError::Database)?;
 
@@ -168,62 +626,8 @@ impl PositionStore for PostgresStore {
 
     #[instrument(skip(self, position), fields(id = %position.id, user = %position.user_address))]
     async fn save_position(&self, position: &Position) -> Result<()> {
-        // Positions are now a regular table with simple UUID primary key.
-        // This simplifies upserts since we don't need composite keys.
-        // Note: updated_at_block uses created_at_block for now (entity doesn't track it separately)
-        sqlx::query(
-            r#"
-            INSERT INTO positions (
-                id, user_address, level, amount, reward_debt, entry_timestamp,
-                last_add_timestamp, ghost_streak, is_alive, is_extracted,
-                exit_reason, exit_timestamp, extracted_amount, extracted_rewards,
-                created_at_block, updated_at_block, updated_at
-            )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $15, $16)
-            ON CONFLICT (id) DO UPDATE SET
-                amount = EXCLUDED.amount,
-                reward_debt = EXCLUDED.reward_debt,
-                last_add_timestamp = EXCLUDED.last_add_timestamp,
-                ghost_streak = EXCLUDED.ghost_streak,
-                is_alive = EXCLUDED.is_alive,
-                is_extracted = EXCLUDED.is_extracted,
-                exit_reason = EXCLUDED.exit_reason,
-                exit_timestamp = EXCLUDED.exit_timestamp,
-                extracted_amount = EXCLUDED.extracted_amount,
-                extracted_rewards = EXCLUDED.extracted_rewards,
-                updated_at_block = $15,
-                updated_at = EXCLUDED.updated_at
-            "#,
-        )
-        .bind(position.id)
-        .bind(position.user_address.as_bytes())
-        .bind(position.level as i16)
-        .bind(position.amount.to_bigdecimal())
-        .bind(position.reward_debt.to_bigdecimal())
-        .bind(position.entry_timestamp)
-        .bind(position.last_add_timestamp)
-        .bind(position.ghost_streak.value())
-        .bind(position.is_alive)
-        .bind(position.is_extracted)
-        .bind(position.exit_reason.map(|r| r.to_string()))
-        .bind(position.exit_timestamp)
-        .bind(
-            position
-                .extracted_amount
-                .as_ref()
-                .map(TokenAmount::to_bigdecimal),
-        )
-        .bind(
-            position
-                .extracted_rewards
-                .as_ref()
-                .map(TokenAmount::to_bigdecimal),
-        )
-        .bind(position.created_at_block.value() as i64)
-        .bind(position.updated_at)
-        .execute(&self.pool)
-        .await
-        .map_err(InfraError::Database)?;
+        let _timer = obs::store_timer("save_position");
+        upsert_position(&mut *self.conn().await?, position).await?;
 
         debug!("Position saved");
         Ok(())
@@ -231,12 +635,14 @@ impl PositionStore for PostgresStore {
 
     #[instrument(skip(self), fields(level = ?level, threshold = threshold))]
     async fn get_at_risk_positions(&self, level: Level, threshold: u32) -> Result<Vec<Position>> {
+        let _timer = obs::store_timer("get_at_risk_positions");
         let rows = sqlx::query_as::<_, PositionRow>(
             r#"
             SELECT id, user_address, level, amount, reward_debt, entry_timestamp,
                    last_add_timestamp, ghost_streak, is_alive, is_extracted,
                    exit_reason, exit_timestamp, extracted_amount, extracted_rewards,
-                   created_at_block, updated_at
+                   created_at_block, updated_at, survival_seconds, scans_survived,
+                   effective_stake
             FROM positions
             WHERE level = $1 AND is_alive = true AND is_extracted = false
             ORDER BY entry_timestamp ASC
@@ -245,7 +651,7 @@ impl PositionStore for PostgresStore {
         )
         .bind(level as i16)
         .bind(threshold as i64)
-        .fetch_all(&self.pool)
+        .fetch_all(&mut *self.conn().await?)
         .await
         .map_err(InfraError::Database)?;
 
@@ -255,46 +661,91 @@ impl PositionStore for PostgresStore {
     }
 
     #[instrument(skip(self, entry), fields(position_id = %entry.position_id, action = ?entry.action))]
-    async fn record_history(&self, entry: &PositionHistoryEntry) -> Result<()> {
-        sqlx::query(
+    async fn append_history(&self, entry: &PositionHistoryEntry) -> Result<()> {
+        let _timer = obs::store_timer("append_history");
+        match &self.batch {
+            Some(batch) => batch.buffer(|rows| rows.history.push(entry.clone())).await,
+            None => insert_history(&mut *self.conn().await?, entry).await?,
+        }
+
+        debug!("Position history recorded");
+        Ok(())
+    }
+
+    #[instrument(
+        skip(self, position, entry, events),
+        fields(id = %position.id, action = ?entry.action, events = events.len())
+    )]
+    async fn save_position_with_history(
+        &self,
+        position: &Position,
+        entry: &PositionHistoryEntry,
+        events: &[OutboxEvent],
+    ) -> Result<()> {
+        let _timer = obs::store_timer("save_position_with_history");
+        let mut tx = self.transaction().await?;
+        upsert_position(&mut *tx, position).await?;
+        insert_outbox_events(&mut tx, events).await?;
+        if let Some(batch) = &self.batch {
+            drop(tx);
+            batch.buffer(|rows| rows.history.push(entry.clone())).await;
+        } else {
+            insert_history(&mut *tx, entry).await?;
+            tx.commit().await?;
+        }
+
+        debug!("Position saved with history");
+        Ok(())
+    }
+
+    #[instrument(skip(self), fields(address = %address, limit = limit))]
+    async fn get_history(
+        &self,
+        address: &EthAddress,
+        limit: u32,
+        before: Option<HistoryCursor>,
+    ) -> Result<Vec<PositionHistoryEntry>> {
+        let _timer = obs::store_timer("get_history");
+        let rows = sqlx::query_as::<_, PositionHistoryRow>(
             r#"
-            INSERT INTO position_history (
-                id, position_id, user_address, action, amount_change, new_total,
-                block_number, timestamp
-            )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            SELECT id, position_id, user_address, action, amount_change, new_total,
+                   level, ghost_streak, block_number, timestamp
+            FROM position_history
+            WHERE user_address = $1
+              AND ($2::timestamptz IS NULL OR (timestamp, id) < ($2, $3))
+            ORDER BY timestamp DESC, id DESC
+            LIMIT $4
             "#,
         )
-        .bind(entry.id)
-        .bind(entry.position_id)
-        .bind(entry.user_address.as_bytes())
-        .bind(entry.action.name())
-        .bind(entry.amount_change.to_bigdecimal())
-        .bind(entry.new_total.to_bigdecimal())
-        .bind(entry.block_number.value() as i64)
-        .bind(entry.timestamp)
-        .execute(&self.pool)
+        .bind(address.as_bytes())
+        .bind(before.map(|c| c.timestamp))
+        .bind(before.map(|c| c.id))
+        .bind(limit as i64)
+        .fetch_all(&mut *self.flushed_conn().await?)
         .await
         .map_err(InfraError::Database)?;
 
-        debug!("Position history recorded");
-        Ok(())
+        rows.into_iter()
+            .map(|r| PositionHistoryEntry::try_from(r).map_err(Into::into))
+            .collect()
     }
 
     #[instrument(skip(self), fields(id = %id))]
     async fn get_position_by_id(&self, id: &Uuid) -> Result<Option<Position>> {
+        let _timer = obs::store_timer("get_position_by_id");
         let row = sqlx::query_as::<_, PositionRow>(
             r#"
             SELECT id, user_address, level, amount, reward_debt, entry_timestamp,
                    last_add_timestamp, ghost_streak, is_alive, is_extracted,
                    exit_reason, exit_timestamp, extracted_amount, extracted_rewards,
-                   created_at_block, updated_at
+                   created_at_block, updated_at, survival_seconds, scans_survived,
+                   effective_stake
             FROM positions
             WHERE id = $1
             "#,
         )
         .bind(id)
-        .fetch_optional(&self.pool)
+        .fetch_optional(&mut *self.conn().await?)
         .await
         .map_err(InfraError::Database)?;
 
@@ -306,19 +757,21 @@ impl PositionStore for PostgresStore {
 
     #[instrument(skip(self), fields(level = ?level))]
     async fn get_positions_by_level(&self, level: Level) -> Result<Vec<Position>> {
+        let _timer = obs::store_timer("get_positions_by_level");
         let rows = sqlx::query_as::<_, PositionRow>(
             r#"
             SELECT id, user_address, level, amount, reward_debt, entry_timestamp,
                    last_add_timestamp, ghost_streak, is_alive, is_extracted,
                    exit_reason, exit_timestamp, extracted_amount, extracted_rewards,
-                   created_at_block, updated_at
+                   created_at_block, updated_at, survival_seconds, scans_survived,
+                   effective_stake
             FROM positions
             WHERE level = $1 AND is_alive = true AND is_extracted = false
             ORDER BY entry_timestamp DESC
             "#,
         )
         .bind(level as i16)
-        .fetch_all(&self.pool)
+        .fetch_all(&mut *self.conn().await?)
         .await
         .map_err(InfraError::Database)?;
 
@@ -327,8 +780,73 @@ impl PositionStore for PostgresStore {
             .collect()
     }
 
+    #[instrument(skip(self, filter))]
+    async fn query(&self, filter: &PositionFilter, limit: u32) -> Result<Page<Position>> {
+        let _timer = obs::store_timer("query_positions");
+        let mut query = QueryBuilder::<Postgres>::new(
+            r#"
+            SELECT id, user_address, level, amount, reward_debt, entry_timestamp,
+                   last_add_timestamp, ghost_streak, is_alive, is_extracted,
+                   exit_reason, exit_timestamp, extracted_amount, extracted_rewards,
+                   created_at_block, updated_at, survival_seconds, scans_survived,
+                   effective_stake
+            FROM positions
+            WHERE TRUE"#,
+        );
+        // Only the conditions given are added, so the planner sees them
+        if let Some(level) = filter.level {
+            query.push(" AND level = ").push_bind(level as i16);
+        }
+        if let Some(min) = &filter.min_amount {
+            query.push(" AND amount >= ").push_bind(min.to_bigdecimal());
+        }
+        if let Some(max) = &filter.max_amount {
+            query.push(" AND amount <= ").push_bind(max.to_bigdecimal());
+        }
+        if let Some(streak) = filter.min_streak {
+            query.push(" AND ghost_streak >= ").push_bind(streak.value());
+        }
+        if filter.active_only {
+            query.push(" AND is_alive = true AND is_extracted = false");
+        }
+        if let Some(after) = filter.created_after {
+            query.push(" AND entry_timestamp > ").push_bind(after);
+        }
+        if let Some(cursor) = filter.after {
+            query
+                .push(" AND (entry_timestamp, id) < (")
+                .push_bind(cursor.entry_timestamp)
+                .push(", ")
+                .push_bind(cursor.id)
+                .push(")");
+        }
+        // One row past the page tells whether there is another
+        query
+            .push(" ORDER BY entry_timestamp DESC, id DESC LIMIT ")
+            .push_bind(i64::from(limit) + 1);
+
+        let rows = query
+            .build_query_as::<PositionRow>()
+            .fetch_all(&mut *self.conn().await?)
+            .await
+            .map_err(InfraError::Database)?;
+
+        let mut items = rows
+            .into_iter()
+            .map(|r| Position::try_from(r).map_err(Into::into))
+            .collect::<Result<Vec<_>>>()?;
+        let next_cursor = (items.len() > limit as usize)
+            .then(|| {
+                items.truncate(limit as usize);
+                items.last().map(|p| PositionCursor::of(p).to_string())
+            })
+            .flatten();
+        Ok(Page { items, next_cursor })
+    }
+
     #[instrument(skip(self), fields(level = ?level))]
     async fn count_positions_by_level(&self, level: Level) -> Result<u32> {
+        let _timer = obs::store_timer("count_positions_by_level");
         let count: i64 = sqlx::query_scalar(
             r#"
             SELECT COUNT(*) FROM positions
@@ -336,7 +854,31 @@ impl PositionStore for PostgresStore {
             "#,
         )
         .bind(level as i16)
-        .fetch_one(&self.pool)
+        .fetch_one(&mut *self.conn().await?)
+        .await
+        .map_err(InfraError::Database)?;
+
+        Ok(count as u32)
+    }
+
+    #[instrument(skip(self), fields(level = ?level))]
+    async fn count_scans_survived(
+        &self,
+        level: Level,
+        entered: BlockNumber,
+        exited: BlockNumber,
+    ) -> Result<u32> {
+        let _timer = obs::store_timer("count_scans_survived");
+        let count: i64 = sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*) FROM scans
+            WHERE level = $1 AND finalized_block > $2 AND finalized_block < $3
+            "#,
+        )
+        .bind(level as i16)
+        .bind(entered.value() as i64)
+        .bind(exited.value() as i64)
+        .fetch_one(&mut *self.conn().await?)
         .await
         .map_err(InfraError::Database)?;
 
@@ -364,6 +906,8 @@ struct ScanRow {
     distributed_upstream: Option<sqlx::types::BigDecimal>,
     protocol_fee: Option<sqlx::types::BigDecimal>,
     survivor_count: Option<i32>,
+    finalized_block: Option<i64>,
+    finalization_latency_ms: Option<i64>,
 }
 
 impl TryFrom<ScanRow> for Scan {
@@ -389,6 +933,8 @@ impl TryFrom<ScanRow> for Scan {
                 .map(|d| TokenAmount::from_bigdecimal(&d)),
             protocol_fee: row.protocol_fee.map(|d| TokenAmount::from_bigdecimal(&d)),
             survivor_count: row.survivor_count.map(|c| c as u32),
+            finalized_block: row.finalized_block.map(|b| BlockNumber::new(b as u64)),
+            finalization_latency_ms: row.finalization_latency_ms.map(|ms| ms as u64),
         })
     }
 }
@@ -397,14 +943,31 @@ impl TryFrom<ScanRow> for Scan {
 impl ScanStore for PostgresStore {
     #[instrument(skip(self, scan), fields(scan_id = %scan.scan_id, level = ?scan.level))]
     async fn save_scan(&self, scan: &Scan) -> Result<()> {
+        let _timer = obs::store_timer("save_scan");
         sqlx::query(
             r#"
             INSERT INTO scans (
                 id, scan_id, level, seed, executed_at, finalized_at,
                 death_count, total_dead, burned, distributed_same_level,
-                distributed_upstream, protocol_fee, survivor_count
+                distributed_upstream, protocol_fee, survivor_count,
+                finalized_block, finalization_latency_ms
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
+            ON CONFLICT (scan_id) DO UPDATE SET
+                level = EXCLUDED.level,
+                seed = EXCLUDED.seed,
+                executed_at = EXCLUDED.executed_at,
+                finalized_at = EXCLUDED.finalized_at,
+                death_count = EXCLUDED.death_count,
+                total_dead = EXCLUDED.total_dead,
+                burned = EXCLUDED.burned,
+                distributed_same_level = EXCLUDED.distributed_same_level,
+                distributed_upstream = EXCLUDED.distributed_upstream,
+                protocol_fee = EXCLUDED.protocol_fee,
+                survivor_count = EXCLUDED.survivor_count,
+                finalized_block = EXCLUDED.finalized_block,
+                finalization_latency_ms = EXCLUDED.finalization_latency_ms
+            WHERE scans.seed = $16
             "#,
         )
         .bind(scan.id)
@@ -428,7 +991,10 @@ impl ScanStore for PostgresStore {
         )
         .bind(scan.protocol_fee.as_ref().map(TokenAmount::to_bigdecimal))
         .bind(scan.survivor_count.map(|c| c as i32))
-        .execute(&self.pool)
+        .bind(scan.finalized_block.map(|b| b.value() as i64))
+        .bind(scan.finalization_latency_ms.map(|ms| ms as i64))
+        .bind(Scan::UNKNOWN_SEED)
+        .execute(&mut *self.conn().await?)
         .await
         .map_err(InfraError::Database)?;
 
@@ -438,6 +1004,7 @@ impl ScanStore for PostgresStore {
 
     #[instrument(skip(self, data), fields(scan_id = %scan_id))]
     async fn finalize_scan(&self, scan_id: &str, data: ScanFinalizationData) -> Result<()> {
+        let _timer = obs::store_timer("finalize_scan");
         let result = sqlx::query(
             r#"
             UPDATE scans SET
@@ -448,7 +1015,9 @@ impl ScanStore for PostgresStore {
                 distributed_same_level = $6,
                 distributed_upstream = $7,
                 protocol_fee = $8,
-                survivor_count = $9
+                survivor_count = $9,
+                finalized_block = $10,
+                finalization_latency_ms = $11
             WHERE scan_id = $1
             "#,
         )
@@ -461,7 +1030,9 @@ impl ScanStore for PostgresStore {
         .bind(data.distributed_upstream.to_bigdecimal())
         .bind(data.protocol_fee.to_bigdecimal())
         .bind(data.survivor_count as i32)
-        .execute(&self.pool)
+        .bind(data.finalized_block.value() as i64)
+        .bind(data.finalization_latency_ms.map(|ms| ms as i64))
+        .execute(&mut *self.conn().await?)
         .await
         .map_err(InfraError::Database)?;
 
@@ -475,11 +1046,13 @@ impl ScanStore for PostgresStore {
 
     #[instrument(skip(self), fields(level = ?level, limit = limit))]
     async fn get_recent_scans(&self, level: Level, limit: u32) -> Result<Vec<Scan>> {
+        let _timer = obs::store_timer("get_recent_scans");
         let rows = sqlx::query_as::<_, ScanRow>(
             r#"
             SELECT id, scan_id, level, seed, executed_at, finalized_at,
                    death_count, total_dead, burned, distributed_same_level,
-                   distributed_upstream, protocol_fee, survivor_count
+                   distributed_upstream, protocol_fee, survivor_count,
+                   finalized_block, finalization_latency_ms
             FROM scans
             WHERE level = $1
             ORDER BY executed_at DESC
@@ -488,7 +1061,7 @@ impl ScanStore for PostgresStore {
         )
         .bind(level as i16)
         .bind(limit as i64)
-        .fetch_all(&self.pool)
+        .fetch_all(&mut *self.conn().await?)
         .await
         .map_err(InfraError::Database)?;
 
@@ -499,17 +1072,19 @@ impl ScanStore for PostgresStore {
 
     #[instrument(skip(self), fields(scan_id = %scan_id))]
     async fn get_scan_by_id(&self, scan_id: &str) -> Result<Option<Scan>> {
+        let _timer = obs::store_timer("get_scan_by_id");
         let row = sqlx::query_as::<_, ScanRow>(
             r#"
             SELECT id, scan_id, level, seed, executed_at, finalized_at,
                    death_count, total_dead, burned, distributed_same_level,
-                   distributed_upstream, protocol_fee, survivor_count
+                   distributed_upstream, protocol_fee, survivor_count,
+                   finalized_block, finalization_latency_ms
             FROM scans
             WHERE scan_id = $1
             "#,
         )
         .bind(scan_id)
-        .fetch_optional(&self.pool)
+        .fetch_optional(&mut *self.conn().await?)
         .await
         .map_err(InfraError::Database)?;
 
@@ -521,17 +1096,19 @@ impl ScanStore for PostgresStore {
 
     #[instrument(skip(self))]
     async fn get_pending_scans(&self) -> Result<Vec<Scan>> {
+        let _timer = obs::store_timer("get_pending_scans");
         let rows = sqlx::query_as::<_, ScanRow>(
             r#"
             SELECT id, scan_id, level, seed, executed_at, finalized_at,
                    death_count, total_dead, burned, distributed_same_level,
-                   distributed_upstream, protocol_fee, survivor_count
+                   distributed_upstream, protocol_fee, survivor_count,
+                   finalized_block, finalization_latency_ms
             FROM scans
             WHERE finalized_at IS NULL
             ORDER BY executed_at ASC
             "#,
         )
-        .fetch_all(&self.pool)
+        .fetch_all(&mut *self.conn().await?)
         .await
         .map_err(InfraError::Database)?;
 
@@ -539,6 +1116,34 @@ impl ScanStore for PostgresStore {
             .map(|r| Scan::try_from(r).map_err(Into::into))
             .collect()
     }
+
+    #[instrument(skip(self, death_ids), fields(scan_id = %scan_id, count = death_ids.len()))]
+    async fn link_deaths_to_scan(&self, scan_id: &str, death_ids: &[Uuid]) -> Result<u64> {
+        let _timer = obs::store_timer("link_deaths_to_scan");
+        let scan_uuid: Option<Uuid> = sqlx::query_scalar("SELECT id FROM scans WHERE scan_id = $1")
+            .bind(scan_id)
+            .fetch_optional(&mut *self.conn().await?)
+            .await
+            .map_err(InfraError::Database)?;
+
+        let Some(uuid) = scan_uuid else {
+            return Err(InfraError::NotFound.into());
+        };
+
+        if death_ids.is_empty() {
+            return Ok(0);
+        }
+
+        let result = sqlx::query("UPDATE deaths SET scan_id = $1 WHERE id = ANY($2)")
+            .bind(uuid)
+            .bind(death_ids)
+            .execute(&mut *self.flushed_conn().await?)
+            .await
+            .map_err(InfraError::Database)?;
+
+        debug!(linked = result.rows_affected(), "Deaths linked to scan");
+        Ok(result.rows_affected())
+    }
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -584,52 +1189,56 @@ impl TryFrom<DeathRow> for Death {
     }
 }
 
+/// Database row for an address's cascade earnings from one scan.
+#[derive(Debug, FromRow)]
+struct ScanCascadeEarningsRow {
+    scan_id: Option<String>,
+    source_level: i16,
+    amount: sqlx::types::BigDecimal,
+    block_number: i64,
+    timestamp: chrono::DateTime<chrono::Utc>,
+}
+
+impl TryFrom<ScanCascadeEarningsRow> for ScanCascadeEarnings {
+    type Error = InfraError;
+
+    fn try_from(row: ScanCascadeEarningsRow) -> std::result::Result<Self, Self::Error> {
+        Ok(ScanCascadeEarnings {
+            scan_id: row.scan_id,
+            source_level: Level::try_from(row.source_level as u8)
+                .map_err(|e| InfraError::Internal(format!("Invalid level in DB: {e}")))?,
+            amount: TokenAmount::from_bigdecimal(&row.amount),
+            block_number: BlockNumber::new(row.block_number as u64),
+            timestamp: row.timestamp,
+        })
+    }
+}
+
 #[async_trait]
 impl DeathStore for PostgresStore {
     #[instrument(skip(self, deaths), fields(count = deaths.len()))]
     async fn record_deaths(&self, deaths: &[Death]) -> Result<()> {
+        let _timer = obs::store_timer("record_deaths");
         if deaths.is_empty() {
             return Ok(());
         }
 
-        // Use a transaction for batch insert
-        let mut tx = self.pool.begin().await.map_err(InfraError::Database)?;
-
-        for death in deaths {
-            sqlx::query(
-                r#"
-                INSERT INTO deaths (
-                    id, scan_id, user_address, position_id, amount_lost,
-                    level, ghost_streak_at_death, created_at
-                )
-                VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
-                "#,
-            )
-            .bind(death.id)
-            .bind(death.scan_id)
-            .bind(death.user_address.as_bytes())
-            .bind(death.position_id)
-            .bind(death.amount_lost.to_bigdecimal())
-            .bind(death.level as i16)
-            .bind(death.ghost_streak_at_death.map(|s| s.value()))
-            .bind(death.created_at)
-            .execute(&mut *tx)
-            .await
-            .map_err(InfraError::Database)?;
+        match &self.batch {
+            Some(batch) => batch.buffer(|rows| rows.deaths.extend_from_slice(deaths)).await,
+            None => batch::insert_deaths(&mut *self.conn().await?, deaths).await?,
         }
 
-        tx.commit().await.map_err(InfraError::Database)?;
-
         debug!(count = deaths.len(), "Deaths recorded");
         Ok(())
     }
 
     #[instrument(skip(self), fields(scan_id = %scan_id))]
     async fn get_deaths_for_scan(&self, scan_id: &str) -> Result<Vec<Death>> {
+        let _timer = obs::store_timer("get_deaths_for_scan");
         // First get the scan's UUID from the on-chain scan_id
         let scan_uuid: Option<Uuid> = sqlx::query_scalar("SELECT id FROM scans WHERE scan_id = $1")
             .bind(scan_id)
-            .fetch_optional(&self.pool)
+            .fetch_optional(&mut *self.conn().await?)
             .await
             .map_err(InfraError::Database)?;
 
@@ -647,7 +1256,7 @@ impl DeathStore for PostgresStore {
             "#,
         )
         .bind(uuid)
-        .fetch_all(&self.pool)
+        .fetch_all(&mut *self.flushed_conn().await?)
         .await
         .map_err(InfraError::Database)?;
 
@@ -658,6 +1267,7 @@ impl DeathStore for PostgresStore {
 
     #[instrument(skip(self), fields(address = %address, limit = limit))]
     async fn get_user_deaths(&self, address: &EthAddress, limit: u32) -> Result<Vec<Death>> {
+        let _timer = obs::store_timer("get_user_deaths");
         let rows = sqlx::query_as::<_, DeathRow>(
             r#"
             SELECT id, scan_id, user_address, position_id, amount_lost,
@@ -670,7 +1280,7 @@ impl DeathStore for PostgresStore {
         )
         .bind(address.as_bytes())
         .bind(limit as i64)
-        .fetch_all(&self.pool)
+        .fetch_all(&mut *self.flushed_conn().await?)
         .await
         .map_err(InfraError::Database)?;
 
@@ -681,9 +1291,10 @@ impl DeathStore for PostgresStore {
 
     #[instrument(skip(self), fields(level = ?level))]
     async fn count_deaths_by_level(&self, level: Level) -> Result<u64> {
+        let _timer = obs::store_timer("count_deaths_by_level");
         let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM deaths WHERE level = $1")
             .bind(level as i16)
-            .fetch_one(&self.pool)
+            .fetch_one(&mut *self.flushed_conn().await?)
             .await
             .map_err(InfraError::Database)?;
 
@@ -692,6 +1303,7 @@ impl DeathStore for PostgresStore {
 
     #[instrument(skip(self), fields(limit = limit))]
     async fn get_recent_deaths(&self, limit: u32) -> Result<Vec<Death>> {
+        let _timer = obs::store_timer("get_recent_deaths");
         let rows = sqlx::query_as::<_, DeathRow>(
             r#"
             SELECT id, scan_id, user_address, position_id, amount_lost,
@@ -702,7 +1314,7 @@ impl DeathStore for PostgresStore {
             "#,
         )
         .bind(limit as i64)
-        .fetch_all(&self.pool)
+        .fetch_all(&mut *self.flushed_conn().await?)
         .await
         .map_err(InfraError::Database)?;
 
@@ -710,55 +1322,449 @@ impl DeathStore for PostgresStore {
             .map(|r| Death::try_from(r).map_err(Into::into))
             .collect()
     }
-}
-
-// ═══════════════════════════════════════════════════════════════════════════════
-// MARKET STORE IMPLEMENTATION (placeholder - to be completed)
-// ═══════════════════════════════════════════════════════════════════════════════
-
-#[async_trait]
-impl MarketStore for PostgresStore {
-    async fn save_round(&self, _round: &Round) -> Result<()> {
-        // TODO: Implement market store
-        Err(InfraError::Internal("Market store not yet implemented".into()).into())
-    }
 
-    async fn record_bet(&self, _bet: &Bet) -> Result<()> {
-        Err(InfraError::Internal("Market store not yet implemented".into()).into())
-    }
-
-    async fn resolve_round(
-        &self,
-        _round_id: &str,
-        _outcome: bool,
-        _burned: &TokenAmount,
-    ) -> Result<()> {
-        Err(InfraError::Internal("Market store not yet implemented".into()).into())
-    }
+    #[instrument(skip(self, shares), fields(count = shares.len()))]
+    async fn record_cascade_shares(&self, shares: &[CascadeShare]) -> Result<()> {
+        let _timer = obs::store_timer("record_cascade_shares");
+        if shares.is_empty() {
+            return Ok(());
+        }
 
-    async fn get_active_rounds(&self, _limit: u32) -> Result<Vec<Round>> {
-        Err(InfraError::Internal("Market store not yet implemented".into()).into())
-    }
+        let mut tx = self.transaction().await?;
 
-    async fn get_round_by_id(&self, _round_id: &str) -> Result<Option<Round>> {
-        Err(InfraError::Internal("Market store not yet implemented".into()).into())
-    }
+        for share in shares {
+            sqlx::query(
+                r#"
+                INSERT INTO cascade_rewards (
+                    id, position_id, user_address, source_level, level,
+                    amount, block_number, created_at
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                ON CONFLICT (created_at, block_number, source_level, position_id) DO NOTHING
+                "#,
+            )
+            .bind(share.id)
+            .bind(share.position_id)
+            .bind(share.user_address.as_bytes())
+            .bind(share.source_level as i16)
+            .bind(share.level as i16)
+            .bind(share.amount.to_bigdecimal())
+            .bind(share.block_number.value() as i64)
+            .bind(share.created_at)
+            .execute(&mut *tx)
+            .await
+            .map_err(InfraError::Database)?;
+        }
 
-    async fn get_bets_for_round(&self, _round_id: &str) -> Result<Vec<Bet>> {
-        Err(InfraError::Internal("Market store not yet implemented".into()).into())
-    }
+        tx.commit().await?;
 
-    async fn get_user_bets(&self, _address: &EthAddress, _limit: u32) -> Result<Vec<Bet>> {
-        Err(InfraError::Internal("Market store not yet implemented".into()).into())
+        debug!(count = shares.len(), "Cascade shares recorded");
+        Ok(())
     }
 
-    async fn mark_bet_claimed(
+    #[instrument(skip(self), fields(address = %address, limit = limit))]
+    async fn get_cascade_earnings(
         &self,
-        _round_id: &str,
-        _user: &EthAddress,
-        _winnings: &TokenAmount,
-    ) -> Result<()> {
-        Err(InfraError::Internal("Market store not yet implemented".into()).into())
+        address: &EthAddress,
+        limit: u32,
+    ) -> Result<CascadeEarnings> {
+        let _timer = obs::store_timer("get_cascade_earnings");
+        let total: sqlx::types::BigDecimal = sqlx::query_scalar(
+            "SELECT COALESCE(SUM(amount), 0) FROM cascade_rewards WHERE user_address = $1",
+        )
+        .bind(address.as_bytes())
+        .fetch_one(&mut *self.conn().await?)
+        .await
+        .map_err(InfraError::Database)?;
+
+        // A distribution shares its block with the `ScanFinalized` of its scan
+        let rows = sqlx::query_as::<_, ScanCascadeEarningsRow>(
+            r#"
+            SELECT s.scan_id, c.source_level, SUM(c.amount) AS amount,
+                   c.block_number, MAX(c.created_at) AS timestamp
+            FROM cascade_rewards c
+            LEFT JOIN scans s
+                ON s.level = c.source_level AND s.finalized_block = c.block_number
+            WHERE c.user_address = $1
+            GROUP BY s.scan_id, c.source_level, c.block_number
+            ORDER BY c.block_number DESC, c.source_level DESC
+            LIMIT $2
+            "#,
+        )
+        .bind(address.as_bytes())
+        .bind(limit as i64)
+        .fetch_all(&mut *self.conn().await?)
+        .await
+        .map_err(InfraError::Database)?;
+
+        Ok(CascadeEarnings {
+            total: TokenAmount::from_bigdecimal(&total),
+            scans: rows
+                .into_iter()
+                .map(ScanCascadeEarnings::try_from)
+                .collect::<std::result::Result<_, _>>()?,
+        })
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// MARKET STORE IMPLEMENTATION
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Database row for rounds.
+#[derive(Debug, FromRow)]
+struct RoundRow {
+    id: Uuid,
+    round_id: String,
+    round_type: i16,
+    target_level: Option<i16>,
+    line: sqlx::types::BigDecimal,
+    deadline: chrono::DateTime<chrono::Utc>,
+    over_pool: sqlx::types::BigDecimal,
+    under_pool: sqlx::types::BigDecimal,
+    is_resolved: bool,
+    outcome: Option<bool>,
+    resolve_time: Option<chrono::DateTime<chrono::Utc>>,
+    total_burned: Option<sqlx::types::BigDecimal>,
+    payout_multiple_bps: Option<i64>,
+    house_take: Option<sqlx::types::BigDecimal>,
+}
+
+impl TryFrom<RoundRow> for Round {
+    type Error = InfraError;
+
+    fn try_from(row: RoundRow) -> std::result::Result<Self, Self::Error> {
+        Ok(Round {
+            id: row.id,
+            round_id: row.round_id,
+            round_type: RoundType::try_from(row.round_type as u8)
+                .map_err(|e| InfraError::Internal(format!("Invalid round type in DB: {e}")))?,
+            target_level: row
+                .target_level
+                .map(|level| Level::try_from(level as u8))
+                .transpose()
+                .map_err(|e| InfraError::Internal(format!("Invalid level in DB: {e}")))?,
+            line: TokenAmount::from_bigdecimal(&row.line),
+            deadline: row.deadline,
+            over_pool: TokenAmount::from_bigdecimal(&row.over_pool),
+            under_pool: TokenAmount::from_bigdecimal(&row.under_pool),
+            is_resolved: row.is_resolved,
+            outcome: row.outcome,
+            resolve_time: row.resolve_time,
+            total_burned: row.total_burned.map(|d| TokenAmount::from_bigdecimal(&d)),
+            payout_multiple_bps: row.payout_multiple_bps.map(|bps| bps as u64),
+            house_take: row.house_take.map(|d| TokenAmount::from_bigdecimal(&d)),
+        })
+    }
+}
+
+/// Database row for bets.
+#[derive(Debug, FromRow)]
+struct BetRow {
+    id: Uuid,
+    round_id: Uuid,
+    user_address: Vec<u8>,
+    amount: sqlx::types::BigDecimal,
+    is_over: bool,
+    block_number: i64,
+    log_index: i64,
+    placed_at: chrono::DateTime<chrono::Utc>,
+    over_pool_before: sqlx::types::BigDecimal,
+    under_pool_before: sqlx::types::BigDecimal,
+    implied_probability_bps: Option<i32>,
+    payout_multiple_bps: Option<i64>,
+    is_claimed: bool,
+    winnings: Option<sqlx::types::BigDecimal>,
+    claimed_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl TryFrom<BetRow> for Bet {
+    type Error = InfraError;
+
+    fn try_from(row: BetRow) -> std::result::Result<Self, Self::Error> {
+        Ok(Bet {
+            id: row.id,
+            round_id: row.round_id,
+            user_address: EthAddress::new(
+                row.user_address
+                    .try_into()
+                    .map_err(|_| InfraError::Internal("Invalid address length in DB".into()))?,
+            ),
+            amount: TokenAmount::from_bigdecimal(&row.amount),
+            is_over: row.is_over,
+            block_number: BlockNumber::new(row.block_number as u64),
+            log_index: row.log_index as u64,
+            placed_at: row.placed_at,
+            pools_before: Pools {
+                over_pool: TokenAmount::from_bigdecimal(&row.over_pool_before),
+                under_pool: TokenAmount::from_bigdecimal(&row.under_pool_before),
+            },
+            implied_probability_bps: row.implied_probability_bps.map(|bps| bps as u32),
+            payout_multiple_bps: row.payout_multiple_bps.map(|bps| bps as u64),
+            is_claimed: row.is_claimed,
+            winnings: row.winnings.map(|d| TokenAmount::from_bigdecimal(&d)),
+            claimed_at: row.claimed_at,
+        })
+    }
+}
+
+/// Columns of [`RoundRow`].
+const ROUND_COLUMNS: &str = "id, round_id, round_type, target_level, line, deadline, \
+     over_pool, under_pool, is_resolved, outcome, resolve_time, total_burned, \
+     payout_multiple_bps, house_take";
+
+/// Columns of [`BetRow`], of the `bets` table aliased `b`.
+const BET_COLUMNS: &str = "b.id, b.round_id, b.user_address, b.amount, b.is_over, \
+     b.block_number, b.log_index, b.placed_at, b.over_pool_before, b.under_pool_before, \
+     b.implied_probability_bps, b.payout_multiple_bps, b.is_claimed, b.winnings, b.claimed_at";
+
+/// Multiple in basis points, as stored.
+fn bps_to_db(bps: u64) -> i64 {
+    i64::try_from(bps).unwrap_or(i64::MAX)
+}
+
+#[async_trait]
+impl MarketStore for PostgresStore {
+    #[instrument(skip(self, round), fields(round_id = %round.round_id))]
+    async fn save_round(&self, round: &Round) -> Result<()> {
+        let _timer = obs::store_timer("save_round");
+        sqlx::query(
+            r#"
+            INSERT INTO rounds (
+                id, round_id, round_type, target_level, line, deadline, over_pool,
+                under_pool, is_resolved, outcome, resolve_time, total_burned,
+                payout_multiple_bps, house_take
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+            ON CONFLICT (round_id) DO UPDATE SET
+                over_pool = EXCLUDED.over_pool,
+                under_pool = EXCLUDED.under_pool,
+                is_resolved = EXCLUDED.is_resolved,
+                outcome = EXCLUDED.outcome,
+                resolve_time = EXCLUDED.resolve_time,
+                total_burned = EXCLUDED.total_burned,
+                payout_multiple_bps = EXCLUDED.payout_multiple_bps,
+                house_take = EXCLUDED.house_take
+            "#,
+        )
+        .bind(round.id)
+        .bind(&round.round_id)
+        .bind(round.round_type as i16)
+        .bind(round.target_level.map(|level| level as i16))
+        .bind(round.line.to_bigdecimal())
+        .bind(round.deadline)
+        .bind(round.over_pool.to_bigdecimal())
+        .bind(round.under_pool.to_bigdecimal())
+        .bind(round.is_resolved)
+        .bind(round.outcome)
+        .bind(round.resolve_time)
+        .bind(round.total_burned.as_ref().map(TokenAmount::to_bigdecimal))
+        .bind(round.payout_multiple_bps.map(bps_to_db))
+        .bind(round.house_take.as_ref().map(TokenAmount::to_bigdecimal))
+        .execute(&mut *self.conn().await?)
+        .await
+        .map_err(InfraError::Database)?;
+
+        Ok(())
+    }
+
+    #[instrument(skip(self, bet), fields(round = %bet.round_id, block = %bet.block_number))]
+    async fn record_bet(&self, bet: &Bet) -> Result<()> {
+        let _timer = obs::store_timer("record_bet");
+        let mut tx = self.transaction().await?;
+
+        let inserted = sqlx::query(
+            r#"
+            INSERT INTO bets (
+                id, round_id, user_address, amount, is_over, block_number, log_index,
+                placed_at, over_pool_before, under_pool_before, implied_probability_bps,
+                payout_multiple_bps, is_claimed, winnings, claimed_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
+            ON CONFLICT (round_id, block_number, log_index) DO NOTHING
+            "#,
+        )
+        .bind(bet.id)
+        .bind(bet.round_id)
+        .bind(bet.user_address.as_bytes())
+        .bind(bet.amount.to_bigdecimal())
+        .bind(bet.is_over)
+        .bind(bet.block_number.value() as i64)
+        .bind(bet.log_index as i64)
+        .bind(bet.placed_at)
+        .bind(bet.pools_before.over_pool.to_bigdecimal())
+        .bind(bet.pools_before.under_pool.to_bigdecimal())
+        .bind(bet.implied_probability_bps.map(|bps| bps as i32))
+        .bind(bet.payout_multiple_bps.map(bps_to_db))
+        .bind(bet.is_claimed)
+        .bind(bet.winnings.as_ref().map(TokenAmount::to_bigdecimal))
+        .bind(bet.claimed_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(InfraError::Database)?
+        .rows_affected();
+
+        // A replayed bet is already in the pools
+        if inserted > 0 {
+            sqlx::query(
+                r#"
+                UPDATE rounds SET
+                    over_pool = over_pool + CASE WHEN $2 THEN $3 ELSE 0 END,
+                    under_pool = under_pool + CASE WHEN $2 THEN 0 ELSE $3 END
+                WHERE id = $1
+                "#,
+            )
+            .bind(bet.round_id)
+            .bind(bet.is_over)
+            .bind(bet.amount.to_bigdecimal())
+            .execute(&mut *tx)
+            .await
+            .map_err(InfraError::Database)?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    #[instrument(skip(self, settlement), fields(outcome = settlement.outcome))]
+    async fn resolve_round(&self, round_id: &str, settlement: &RoundSettlement) -> Result<()> {
+        let _timer = obs::store_timer("resolve_round");
+        let resolved = sqlx::query(
+            r#"
+            UPDATE rounds SET
+                is_resolved = TRUE,
+                outcome = $2,
+                resolve_time = $3,
+                total_burned = $4,
+                payout_multiple_bps = $5,
+                house_take = $6
+            WHERE round_id = $1 AND NOT is_resolved
+            "#,
+        )
+        .bind(round_id)
+        .bind(settlement.outcome)
+        .bind(settlement.resolved_at)
+        .bind(settlement.burned.to_bigdecimal())
+        .bind(settlement.payout_multiple_bps.map(bps_to_db))
+        .bind(settlement.house_take.to_bigdecimal())
+        .execute(&mut *self.conn().await?)
+        .await
+        .map_err(InfraError::Database)?
+        .rows_affected();
+
+        if resolved == 0 {
+            return Err(InfraError::NotFound.into());
+        }
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn get_active_rounds(&self, limit: u32) -> Result<Vec<Round>> {
+        let _timer = obs::store_timer("get_active_rounds");
+        let rows = sqlx::query_as::<_, RoundRow>(&format!(
+            "SELECT {ROUND_COLUMNS} FROM rounds WHERE NOT is_resolved \
+             ORDER BY deadline ASC LIMIT $1"
+        ))
+        .bind(limit as i64)
+        .fetch_all(&mut *self.conn().await?)
+        .await
+        .map_err(InfraError::Database)?;
+
+        rows.into_iter()
+            .map(|r| Round::try_from(r).map_err(Into::into))
+            .collect()
+    }
+
+    #[instrument(skip(self))]
+    async fn get_round_by_id(&self, round_id: &str) -> Result<Option<Round>> {
+        let _timer = obs::store_timer("get_round_by_id");
+        let row = sqlx::query_as::<_, RoundRow>(&format!(
+            "SELECT {ROUND_COLUMNS} FROM rounds WHERE round_id = $1"
+        ))
+        .bind(round_id)
+        .fetch_optional(&mut *self.conn().await?)
+        .await
+        .map_err(InfraError::Database)?;
+
+        row.map(Round::try_from).transpose().map_err(Into::into)
+    }
+
+    #[instrument(skip(self))]
+    async fn get_bets_for_round(&self, round_id: &str) -> Result<Vec<Bet>> {
+        let _timer = obs::store_timer("get_bets_for_round");
+        let rows = sqlx::query_as::<_, BetRow>(&format!(
+            "SELECT {BET_COLUMNS} FROM bets b \
+             JOIN rounds r ON r.id = b.round_id \
+             WHERE r.round_id = $1 \
+             ORDER BY b.block_number ASC, b.log_index ASC"
+        ))
+        .bind(round_id)
+        .fetch_all(&mut *self.conn().await?)
+        .await
+        .map_err(InfraError::Database)?;
+
+        rows.into_iter()
+            .map(|r| Bet::try_from(r).map_err(Into::into))
+            .collect()
+    }
+
+    #[instrument(skip(self), fields(address = %address))]
+    async fn get_user_bets(&self, address: &EthAddress, limit: u32) -> Result<Vec<Bet>> {
+        let _timer = obs::store_timer("get_user_bets");
+        let rows = sqlx::query_as::<_, BetRow>(&format!(
+            "SELECT {BET_COLUMNS} FROM bets b \
+             WHERE b.user_address = $1 \
+             ORDER BY b.placed_at DESC, b.log_index DESC LIMIT $2"
+        ))
+        .bind(address.as_bytes())
+        .bind(limit as i64)
+        .fetch_all(&mut *self.conn().await?)
+        .await
+        .map_err(InfraError::Database)?;
+
+        rows.into_iter()
+            .map(|r| Bet::try_from(r).map_err(Into::into))
+            .collect()
+    }
+
+    #[instrument(skip(self, winnings), fields(user = %user))]
+    async fn mark_bet_claimed(
+        &self,
+        round_id: &str,
+        user: &EthAddress,
+        winnings: &TokenAmount,
+    ) -> Result<()> {
+        let _timer = obs::store_timer("mark_bet_claimed");
+        // A user adding to their bet has several rows; the claim pays them
+        // all at once, recorded on the first
+        let claimed = sqlx::query(
+            r#"
+            WITH user_bets AS (
+                SELECT b.id, ROW_NUMBER() OVER (ORDER BY b.block_number, b.log_index) AS n
+                FROM bets b
+                JOIN rounds r ON r.id = b.round_id
+                WHERE r.round_id = $1 AND b.user_address = $2
+            )
+            UPDATE bets SET
+                is_claimed = TRUE,
+                winnings = CASE WHEN user_bets.n = 1 THEN $3 END,
+                claimed_at = now()
+            FROM user_bets
+            WHERE bets.id = user_bets.id
+            "#,
+        )
+        .bind(round_id)
+        .bind(user.as_bytes())
+        .bind(winnings.to_bigdecimal())
+        .execute(&mut *self.conn().await?)
+        .await
+        .map_err(InfraError::Database)?
+        .rows_affected();
+
+        if claimed == 0 {
+            return Err(InfraError::NotFound.into());
+        }
+        Ok(())
     }
 }
 
@@ -773,12 +1779,13 @@ const MEGAETH_CHAIN_ID: i64 = 6342;
 impl IndexerStateStore for PostgresStore {
     #[instrument(skip(self))]
     async fn get_last_block(&self) -> Result<BlockNumber> {
+        let _timer = obs::store_timer("get_last_block");
         // indexer_state is now keyed by chain_id (single row per chain)
         let row: Option<i64> = sqlx::query_scalar(
             "SELECT last_block FROM indexer_state WHERE chain_id = $1",
         )
         .bind(MEGAETH_CHAIN_ID)
-        .fetch_optional(&self.pool)
+        .fetch_optional(&mut *self.conn().await?)
         .await
         .map_err(InfraError::Database)?;
 
@@ -787,6 +1794,7 @@ impl IndexerStateStore for PostgresStore {
 
     #[instrument(skip(self), fields(block = %block.value()))]
     async fn set_last_block(&self, block: BlockNumber, hash: B256) -> Result<()> {
+        let _timer = obs::store_timer("set_last_block");
         // Upsert the indexer state for MegaETH chain
         sqlx::query(
             r#"
@@ -801,7 +1809,7 @@ impl IndexerStateStore for PostgresStore {
         .bind(MEGAETH_CHAIN_ID)
         .bind(block.value() as i64)
         .bind(hash.as_slice())
-        .execute(&self.pool)
+        .execute(&mut *self.conn().await?)
         .await
         .map_err(InfraError::Database)?;
 
@@ -810,6 +1818,7 @@ impl IndexerStateStore for PostgresStore {
     }
 
     #[instrument(skip(self), fields(block = %block.value()))]
+    #[allow(clippy::cast_precision_loss)] // Unix timestamps fit in f64's mantissa
     async fn insert_block_hash(
         &self,
         block: BlockNumber,
@@ -817,6 +1826,7 @@ impl IndexerStateStore for PostgresStore {
         parent: B256,
         timestamp: u64,
     ) -> Result<()> {
+        let _timer = obs::store_timer("insert_block_hash");
         // block_history is a hypertable - insert new rows, let retention policy handle cleanup.
         // Convert Unix timestamp to TIMESTAMPTZ using to_timestamp().
         sqlx::query(
@@ -830,7 +1840,7 @@ impl IndexerStateStore for PostgresStore {
         .bind(hash.as_slice())
         .bind(parent.as_slice())
         .bind(timestamp as f64)
-        .execute(&self.pool)
+        .execute(&mut *self.conn().await?)
         .await
         .map_err(InfraError::Database)?;
 
@@ -839,10 +1849,11 @@ impl IndexerStateStore for PostgresStore {
 
     #[instrument(skip(self), fields(block = %block.value()))]
     async fn get_block_hash(&self, block: BlockNumber) -> Result<Option<B256>> {
+        let _timer = obs::store_timer("get_block_hash");
         let row: Option<Vec<u8>> =
             sqlx::query_scalar("SELECT block_hash FROM block_history WHERE block_number = $1")
                 .bind(block.value() as i64)
-                .fetch_optional(&self.pool)
+                .fetch_optional(&mut *self.conn().await?)
                 .await
                 .map_err(InfraError::Database)?;
 
@@ -859,7 +1870,8 @@ impl IndexerStateStore for PostgresStore {
 
     #[instrument(skip(self), fields(fork_point = %fork_point.value()))]
     async fn execute_reorg_rollback(&self, fork_point: BlockNumber) -> Result<()> {
-        let mut tx = self.pool.begin().await.map_err(InfraError::Database)?;
+        let _timer = obs::store_timer("execute_reorg_rollback");
+        let mut tx = self.transaction().await?;
 
         // Delete block history after fork point
         sqlx::query("DELETE FROM block_history WHERE block_number > $1")
@@ -876,6 +1888,49 @@ impl IndexerStateStore for PostgresStore {
             .await
             .map_err(InfraError::Database)?;
 
+        sqlx::query(
+            "UPDATE contract_cursors SET last_block = $1, updated_at = NOW() \
+             WHERE chain_id = $2 AND last_block > $1",
+        )
+        .bind(fork_point.value() as i64)
+        .bind(MEGAETH_CHAIN_ID)
+        .execute(&mut *tx)
+        .await
+        .map_err(InfraError::Database)?;
+
+        // Events of orphaned blocks that are not out yet are never published
+        sqlx::query("DELETE FROM event_outbox WHERE block_number > $1 AND published_at IS NULL")
+            .bind(fork_point.value() as i64)
+            .execute(&mut *tx)
+            .await
+            .map_err(InfraError::Database)?;
+
+        sqlx::query("DELETE FROM raw_logs WHERE block_number > $1")
+            .bind(fork_point.value() as i64)
+            .execute(&mut *tx)
+            .await
+            .map_err(InfraError::Database)?;
+
+        sqlx::query("DELETE FROM quarantined_events WHERE block_number > $1")
+            .bind(fork_point.value() as i64)
+            .execute(&mut *tx)
+            .await
+            .map_err(InfraError::Database)?;
+
+        // Scans finalized in orphaned blocks are recorded again once re-indexed
+        sqlx::query("DELETE FROM level_scan_stats WHERE block_number > $1")
+            .bind(fork_point.value() as i64)
+            .execute(&mut *tx)
+            .await
+            .map_err(InfraError::Database)?;
+
+        // Orphaned transfers stay in the balances until read from the chain
+        sqlx::query("UPDATE holder_balances SET needs_reconciliation = TRUE WHERE last_block > $1")
+            .bind(fork_point.value() as i64)
+            .execute(&mut *tx)
+            .await
+            .map_err(InfraError::Database)?;
+
         // Note: In a real implementation, we'd also need to:
         // - Delete positions created after fork_point
         // - Delete scans executed after fork_point
@@ -883,7 +1938,7 @@ impl IndexerStateStore for PostgresStore {
         // - Update any positions modified after fork_point
         // This requires tracking block numbers on all entities
 
-        tx.commit().await.map_err(InfraError::Database)?;
+        tx.commit().await?;
 
         debug!("Reorg rollback executed");
         Ok(())
@@ -891,11 +1946,12 @@ impl IndexerStateStore for PostgresStore {
 
     #[instrument(skip(self), fields(keep_blocks = keep_blocks))]
     async fn prune_old_blocks(&self, keep_blocks: u64) -> Result<u64> {
+        let _timer = obs::store_timer("prune_old_blocks");
         // block_history has a retention policy - this is now mostly a no-op
         // but we can still manually prune if needed
         let max_block: Option<i64> =
             sqlx::query_scalar("SELECT MAX(block_number) FROM block_history")
-                .fetch_optional(&self.pool)
+                .fetch_optional(&mut *self.conn().await?)
                 .await
                 .map_err(InfraError::Database)?;
 
@@ -910,40 +1966,2189 @@ impl IndexerStateStore for PostgresStore {
 
         let result = sqlx::query("DELETE FROM block_history WHERE block_number < $1")
             .bind(cutoff)
-            .execute(&self.pool)
+            .execute(&mut *self.conn().await?)
             .await
             .map_err(InfraError::Database)?;
 
         debug!(pruned = result.rows_affected(), "Old blocks pruned");
         Ok(result.rows_affected())
     }
+
+    #[instrument(skip(self))]
+    async fn get_cursor(&self, contract: Contract) -> Result<Option<BlockNumber>> {
+        let _timer = obs::store_timer("get_cursor");
+        let row: Option<i64> = sqlx::query_scalar(
+            "SELECT last_block FROM contract_cursors WHERE chain_id = $1 AND contract = $2",
+        )
+        .bind(MEGAETH_CHAIN_ID)
+        .bind(contract.as_str())
+        .fetch_optional(&mut *self.conn().await?)
+        .await
+        .map_err(InfraError::Database)?;
+
+        Ok(row.map(|block| BlockNumber::new(block as u64)))
+    }
+
+    #[instrument(skip(self), fields(block = %block.value()))]
+    async fn set_cursor(&self, contract: Contract, block: BlockNumber) -> Result<()> {
+        let _timer = obs::store_timer("set_cursor");
+        sqlx::query(
+            r#"
+            INSERT INTO contract_cursors (chain_id, contract, last_block, updated_at)
+            VALUES ($1, $2, $3, NOW())
+            ON CONFLICT (chain_id, contract) DO UPDATE SET
+                last_block = EXCLUDED.last_block,
+                updated_at = NOW()
+            "#,
+        )
+        .bind(MEGAETH_CHAIN_ID)
+        .bind(contract.as_str())
+        .bind(block.value() as i64)
+        .execute(&mut *self.conn().await?)
+        .await
+        .map_err(InfraError::Database)?;
+
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn min_cursor(&self, contracts: &[Contract]) -> Result<Option<BlockNumber>> {
+        let _timer = obs::store_timer("min_cursor");
+        if contracts.is_empty() {
+            return Ok(None);
+        }
+
+        let mut names: Vec<&str> = contracts.iter().map(Contract::as_str).collect();
+        names.sort_unstable();
+        names.dedup();
+        let rows: Vec<(String, i64)> = sqlx::query_as(
+            "SELECT contract, last_block FROM contract_cursors \
+             WHERE chain_id = $1 AND contract = ANY($2)",
+        )
+        .bind(MEGAETH_CHAIN_ID)
+        .bind(&names)
+        .fetch_all(&mut *self.conn().await?)
+        .await
+        .map_err(InfraError::Database)?;
+
+        // Contracts without a row have indexed nothing yet
+        let min = if rows.len() < names.len() {
+            0
+        } else {
+            rows.iter().map(|(_, block)| *block).min().unwrap_or(0)
+        };
+        Ok(Some(BlockNumber::new(min as u64)))
+    }
+
+    #[instrument(skip(self))]
+    async fn commit_batch(&self) -> Result<()> {
+        let Some(batch) = &self.batch else {
+            return Ok(());
+        };
+        let _timer = obs::store_timer("commit_batch");
+        batch.commit(&self.pool, &self.retry).await
+    }
+
+    #[instrument(skip(self))]
+    async fn discard_batch(&self) -> Result<()> {
+        match &self.batch {
+            Some(batch) => batch.rollback().await,
+            None => Ok(()),
+        }
+    }
+
+    async fn ping(&self) -> Result<()> {
+        // Straight to the pool: the batch's transaction may be the broken one
+        sqlx::query("SELECT 1")
+            .execute(&self.pool)
+            .await
+            .map_err(InfraError::Database)?;
+        Ok(())
+    }
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
-// STATS STORE IMPLEMENTATION (placeholder - to be completed)
+// STATS STORE IMPLEMENTATION
 // ═══════════════════════════════════════════════════════════════════════════════
 
-#[async_trait]
-impl StatsStore for PostgresStore {
-    async fn get_global_stats(&self) -> Result<GlobalStats> {
-        // TODO: Implement stats store
-        Err(InfraError::Internal("Stats store not yet implemented".into()).into())
-    }
+/// Database row for level stats.
+#[derive(Debug, FromRow)]
+struct LevelStatsRow {
+    level: i16,
+    total_staked: sqlx::types::BigDecimal,
+    total_effective_staked: sqlx::types::BigDecimal,
+    alive_count: i32,
+    total_deaths: i32,
+    deaths_24h: i64,
+    total_extracted: i32,
+    total_culled: i32,
+    total_burned: sqlx::types::BigDecimal,
+    total_distributed: sqlx::types::BigDecimal,
+    highest_ghost_streak: i32,
+    total_ghost_streak: i64,
+    total_survival_seconds: i64,
+    survival_samples: i32,
+    updated_at: chrono::DateTime<chrono::Utc>,
+}
 
-    async fn get_level_stats(&self, _level: Level) -> Result<LevelStats> {
-        Err(InfraError::Internal("Stats store not yet implemented".into()).into())
-    }
+impl TryFrom<LevelStatsRow> for LevelStats {
+    type Error = InfraError;
 
-    async fn update_level_stats(&self, _level: Level, _delta: LevelStatsDelta) -> Result<()> {
-        Err(InfraError::Internal("Stats store not yet implemented".into()).into())
+    fn try_from(row: LevelStatsRow) -> std::result::Result<Self, Self::Error> {
+        Ok(LevelStats {
+            level: Level::try_from(row.level as u8)
+                .map_err(|e| InfraError::Internal(format!("Invalid level in DB: {e}")))?,
+            total_staked: TokenAmount::from_bigdecimal(&row.total_staked),
+            total_effective_staked: TokenAmount::from_bigdecimal(&row.total_effective_staked),
+            alive_count: row.alive_count.max(0) as u32,
+            total_deaths: row.total_deaths.max(0) as u32,
+            deaths_24h: row.deaths_24h.max(0) as u32,
+            total_extracted: row.total_extracted.max(0) as u32,
+            total_culled: row.total_culled.max(0) as u32,
+            total_burned: TokenAmount::from_bigdecimal(&row.total_burned),
+            total_distributed: TokenAmount::from_bigdecimal(&row.total_distributed),
+            highest_ghost_streak: GhostStreak::new(row.highest_ghost_streak)
+                .map_err(|e| InfraError::Internal(format!("Invalid ghost streak in DB: {e}")))?,
+            total_ghost_streak: row.total_ghost_streak.max(0) as u64,
+            total_survival_seconds: row.total_survival_seconds.max(0) as u64,
+            survival_samples: row.survival_samples.max(0) as u32,
+            updated_at: row.updated_at,
+        })
     }
+}
 
-    async fn get_all_level_stats(&self) -> Result<Vec<LevelStats>> {
-        Err(InfraError::Internal("Stats store not yet implemented".into()).into())
+/// Database row for global stats.
+#[derive(Debug, FromRow)]
+struct GlobalStatsRow {
+    total_value_locked: sqlx::types::BigDecimal,
+    total_positions: i32,
+    total_deaths: i32,
+    total_burned: sqlx::types::BigDecimal,
+    total_emissions_distributed: sqlx::types::BigDecimal,
+    total_toll_collected: sqlx::types::BigDecimal,
+    total_buyback_burned: sqlx::types::BigDecimal,
+    system_reset_count: i32,
+    updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<GlobalStatsRow> for GlobalStats {
+    fn from(row: GlobalStatsRow) -> Self {
+        GlobalStats {
+            total_value_locked: TokenAmount::from_bigdecimal(&row.total_value_locked),
+            total_positions: row.total_positions.max(0) as u32,
+            total_deaths: row.total_deaths.max(0) as u32,
+            total_burned: TokenAmount::from_bigdecimal(&row.total_burned),
+            total_emissions_distributed: TokenAmount::from_bigdecimal(
+                &row.total_emissions_distributed,
+            ),
+            total_toll_collected: TokenAmount::from_bigdecimal(&row.total_toll_collected),
+            total_buyback_burned: TokenAmount::from_bigdecimal(&row.total_buyback_burned),
+            system_reset_count: row.system_reset_count.max(0) as u32,
+            updated_at: row.updated_at,
+        }
     }
+}
 
-    async fn refresh_global_stats(&self) -> Result<GlobalStats> {
-        Err(InfraError::Internal("Stats store not yet implemented".into()).into())
+/// Level stats columns, with `deaths_24h` computed from finalized scans and
+/// scan-less death records (system resets).
+const LEVEL_STATS_SELECT: &str = r"
+    SELECT
+        ls.level, ls.total_staked, ls.total_effective_staked, ls.alive_count, ls.total_deaths,
+        (
+            SELECT COUNT(*) FROM deaths d
+            WHERE d.level = ls.level AND d.scan_id IS NULL
+              AND d.created_at > NOW() - INTERVAL '24 hours'
+        ) + (
+            SELECT COALESCE(SUM(s.death_count), 0) FROM scans s
+            WHERE s.level = ls.level AND s.finalized_at > NOW() - INTERVAL '24 hours'
+        )::BIGINT AS deaths_24h,
+        ls.total_extracted, ls.total_culled, ls.total_burned, ls.total_distributed,
+        ls.highest_ghost_streak, ls.total_ghost_streak, ls.total_survival_seconds,
+        ls.survival_samples, ls.updated_at
+    FROM level_stats ls
+";
+
+/// Bind value for an optional amount delta.
+fn amount_or_zero(amount: Option<&TokenAmount>) -> sqlx::types::BigDecimal {
+    amount.map(TokenAmount::to_bigdecimal).unwrap_or_default()
+}
+
+#[async_trait]
+impl StatsStore for PostgresStore {
+    #[instrument(skip(self))]
+    async fn get_global_stats(&self) -> Result<GlobalStats> {
+        let _timer = obs::store_timer("get_global_stats");
+        let row = sqlx::query_as::<_, GlobalStatsRow>(
+            r#"
+            SELECT total_value_locked, total_positions, total_deaths, total_burned,
+                   total_emissions_distributed, total_toll_collected, total_buyback_burned,
+                   system_reset_count, updated_at
+            FROM global_stats
+            WHERE id = 1
+            "#,
+        )
+        .fetch_optional(&mut *self.conn().await?)
+        .await
+        .map_err(InfraError::Database)?
+        .ok_or(InfraError::NotFound)?;
+
+        Ok(row.into())
+    }
+
+    #[instrument(skip(self), fields(level = ?level))]
+    async fn get_level_stats(&self, level: Level) -> Result<LevelStats> {
+        let _timer = obs::store_timer("get_level_stats");
+        let row = sqlx::query_as::<_, LevelStatsRow>(&format!(
+            "{LEVEL_STATS_SELECT} WHERE ls.level = $1"
+        ))
+        .bind(level as i16)
+        .fetch_optional(&mut *self.flushed_conn().await?)
+        .await
+        .map_err(InfraError::Database)?
+        .ok_or(InfraError::NotFound)?;
+
+        Ok(row.try_into()?)
+    }
+
+    #[instrument(skip(self, delta), fields(level = ?level))]
+    async fn update_level_stats(&self, level: Level, delta: LevelStatsDelta) -> Result<()> {
+        let _timer = obs::store_timer("update_level_stats");
+        sqlx::query(
+            r#"
+            UPDATE level_stats SET
+                total_staked = GREATEST(total_staked + $2 - $3, 0),
+                alive_count = GREATEST(alive_count + $4, 0),
+                total_deaths = total_deaths + $5,
+                total_extracted = total_extracted + $6,
+                total_burned = total_burned + $7,
+                total_distributed = total_distributed + $8,
+                highest_ghost_streak = GREATEST(highest_ghost_streak, $9),
+                total_ghost_streak = GREATEST(total_ghost_streak + $10, 0),
+                total_culled = total_culled + $11,
+                total_survival_seconds = GREATEST(total_survival_seconds + $12, 0),
+                survival_samples = survival_samples + $13,
+                total_effective_staked = GREATEST(total_effective_staked + $14 - $15, 0),
+                updated_at = NOW()
+            WHERE level = $1
+            "#,
+        )
+        .bind(level as i16)
+        .bind(amount_or_zero(delta.staked_delta.as_ref()))
+        .bind(amount_or_zero(delta.unstaked_delta.as_ref()))
+        .bind(delta.alive_delta.unwrap_or(0))
+        .bind(delta.deaths_delta.unwrap_or(0) as i32)
+        .bind(delta.extracted_delta.unwrap_or(0) as i32)
+        .bind(amount_or_zero(delta.burned_delta.as_ref()))
+        .bind(amount_or_zero(delta.distributed_delta.as_ref()))
+        .bind(delta.new_highest_streak.map_or(0, |s| s.value()))
+        .bind(delta.streak_delta.unwrap_or(0))
+        .bind(delta.culled_delta.unwrap_or(0) as i32)
+        .bind(delta.survival_seconds_delta.unwrap_or(0))
+        .bind(delta.survival_samples_delta.unwrap_or(0) as i32)
+        .bind(amount_or_zero(delta.effective_staked_delta.as_ref()))
+        .bind(amount_or_zero(delta.effective_unstaked_delta.as_ref()))
+        .execute(&mut *self.conn().await?)
+        .await
+        .map_err(InfraError::Database)?;
+
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn get_all_level_stats(&self) -> Result<Vec<LevelStats>> {
+        let _timer = obs::store_timer("get_all_level_stats");
+        let rows = sqlx::query_as::<_, LevelStatsRow>(&format!(
+            "{LEVEL_STATS_SELECT} ORDER BY ls.level"
+        ))
+        .fetch_all(&mut *self.flushed_conn().await?)
+        .await
+        .map_err(InfraError::Database)?;
+
+        rows.into_iter()
+            .map(|r| r.try_into().map_err(Into::into))
+            .collect()
+    }
+
+    #[instrument(skip(self))]
+    async fn refresh_global_stats(&self) -> Result<GlobalStats> {
+        let _timer = obs::store_timer("refresh_global_stats");
+        let row = sqlx::query_as::<_, GlobalStatsRow>(
+            r#"
+            UPDATE global_stats g SET
+                total_value_locked = s.tvl,
+                total_positions = s.positions,
+                total_deaths = s.deaths,
+                updated_at = NOW()
+            FROM (
+                SELECT
+                    COALESCE(SUM(total_staked), 0) AS tvl,
+                    COALESCE(SUM(alive_count), 0)::INTEGER AS positions,
+                    COALESCE(SUM(total_deaths), 0)::INTEGER AS deaths
+                FROM level_stats
+            ) s
+            WHERE g.id = 1
+            RETURNING g.total_value_locked, g.total_positions, g.total_deaths, g.total_burned,
+                      g.total_emissions_distributed, g.total_toll_collected,
+                      g.total_buyback_burned, g.system_reset_count, g.updated_at
+            "#,
+        )
+        .fetch_optional(&mut *self.conn().await?)
+        .await
+        .map_err(InfraError::Database)?
+        .ok_or(InfraError::NotFound)?;
+
+        Ok(row.into())
+    }
+
+    #[instrument(skip(self, delta))]
+    async fn update_global_stats(&self, delta: GlobalStatsDelta) -> Result<()> {
+        let _timer = obs::store_timer("update_global_stats");
+        sqlx::query(
+            r#"
+            UPDATE global_stats SET
+                total_burned = total_burned + $1,
+                total_emissions_distributed = total_emissions_distributed + $2,
+                total_toll_collected = total_toll_collected + $3,
+                total_buyback_burned = total_buyback_burned + $4,
+                system_reset_count = system_reset_count + $5,
+                updated_at = NOW()
+            WHERE id = 1
+            "#,
+        )
+        .bind(amount_or_zero(delta.burned_delta.as_ref()))
+        .bind(amount_or_zero(delta.emissions_delta.as_ref()))
+        .bind(amount_or_zero(delta.toll_delta.as_ref()))
+        .bind(amount_or_zero(delta.buyback_burned_delta.as_ref()))
+        .bind(delta.system_resets_delta.unwrap_or(0) as i32)
+        .execute(&mut *self.conn().await?)
+        .await
+        .map_err(InfraError::Database)?;
+
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn recompute_level_stats(&self) -> Result<Vec<LevelStats>> {
+        let _timer = obs::store_timer("recompute_level_stats");
+        let mut conn = self.flushed_conn().await?;
+        let reweighed = reweigh_positions(&mut *conn, None, chrono::Utc::now()).await?;
+        let result = sqlx::query(
+            r#"
+            WITH pos AS (
+                SELECT
+                    level,
+                    COALESCE(SUM(amount) FILTER (WHERE is_alive AND NOT is_extracted), 0)
+                        AS total_staked,
+                    COALESCE(SUM(effective_stake) FILTER (WHERE is_alive AND NOT is_extracted), 0)
+                        AS total_effective_staked,
+                    COUNT(*) FILTER (WHERE is_alive AND NOT is_extracted)::INTEGER
+                        AS alive_count,
+                    COUNT(*) FILTER (WHERE is_extracted)::INTEGER AS total_extracted,
+                    COUNT(*) FILTER (WHERE exit_reason = 'Culled')::INTEGER AS total_culled,
+                    COALESCE(MAX(ghost_streak), 0) AS highest_ghost_streak,
+                    COALESCE(SUM(ghost_streak) FILTER (WHERE is_alive AND NOT is_extracted), 0)::BIGINT
+                        AS total_ghost_streak,
+                    COALESCE(SUM(survival_seconds), 0)::BIGINT AS total_survival_seconds,
+                    COUNT(survival_seconds)::INTEGER AS survival_samples
+                FROM positions
+                GROUP BY level
+            ),
+            dead AS (
+                SELECT
+                    level,
+                    COUNT(*) FILTER (WHERE scan_id IS NULL)::INTEGER AS total_deaths,
+                    COALESCE(MAX(ghost_streak_at_death), 0) AS highest_ghost_streak
+                FROM deaths
+                GROUP BY level
+            ),
+            scanned AS (
+                SELECT level, COALESCE(SUM(death_count), 0)::INTEGER AS total_deaths
+                FROM scans
+                WHERE finalized_at IS NOT NULL
+                GROUP BY level
+            )
+            UPDATE level_stats ls SET
+                total_staked = COALESCE(pos.total_staked, 0),
+                total_effective_staked = COALESCE(pos.total_effective_staked, 0),
+                alive_count = COALESCE(pos.alive_count, 0),
+                total_extracted = COALESCE(pos.total_extracted, 0),
+                total_deaths = COALESCE(dead.total_deaths, 0) + COALESCE(scanned.total_deaths, 0),
+                highest_ghost_streak = GREATEST(
+                    COALESCE(pos.highest_ghost_streak, 0),
+                    COALESCE(dead.highest_ghost_streak, 0)
+                ),
+                total_ghost_streak = COALESCE(pos.total_ghost_streak, 0),
+                total_culled = COALESCE(pos.total_culled, 0),
+                total_survival_seconds = COALESCE(pos.total_survival_seconds, 0),
+                survival_samples = COALESCE(pos.survival_samples, 0),
+                updated_at = NOW()
+            FROM level_stats base
+            LEFT JOIN pos ON pos.level = base.level
+            LEFT JOIN dead ON dead.level = base.level
+            LEFT JOIN scanned ON scanned.level = base.level
+            WHERE ls.level = base.level
+            "#,
+        )
+        .execute(&mut *conn)
+        .await
+        .map_err(InfraError::Database)?;
+        drop(conn);
+
+        debug!(
+            levels = result.rows_affected(),
+            reweighed = reweighed.len(),
+            "Level stats recomputed"
+        );
+        self.get_all_level_stats().await
+    }
+
+    #[instrument(skip(self))]
+    async fn get_recent_death_counts(
+        &self,
+        since: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<(Level, chrono::DateTime<chrono::Utc>, u32)>> {
+        let _timer = obs::store_timer("get_recent_death_counts");
+        let rows = sqlx::query_as::<_, (i16, chrono::DateTime<chrono::Utc>, i64)>(
+            r#"
+            SELECT level, bucket, SUM(deaths)::BIGINT
+            FROM (
+                SELECT level, date_trunc('minute', created_at) AS bucket, COUNT(*) AS deaths
+                FROM deaths
+                WHERE scan_id IS NULL AND created_at > $1
+                GROUP BY level, bucket
+                UNION ALL
+                SELECT level, date_trunc('minute', finalized_at) AS bucket,
+                       SUM(death_count)::BIGINT AS deaths
+                FROM scans
+                WHERE finalized_at > $1
+                GROUP BY level, bucket
+            ) recent
+            GROUP BY level, bucket
+            ORDER BY bucket
+            "#,
+        )
+        .bind(since)
+        .fetch_all(&mut *self.flushed_conn().await?)
+        .await
+        .map_err(InfraError::Database)?;
+
+        rows.into_iter()
+            .map(|(level, bucket, count)| {
+                let level = Level::try_from(level as u8)
+                    .map_err(|e| InfraError::Internal(format!("Invalid level in DB: {e}")))?;
+                Ok((level, bucket, count.max(0) as u32))
+            })
+            .collect()
+    }
+
+    #[instrument(skip(self))]
+    async fn get_survival_stats(&self) -> Result<Vec<LevelSurvival>> {
+        let _timer = obs::store_timer("get_survival_stats");
+        // Unknown-entry exits sit at level 0 and superseded rows are not exits
+        let rows = sqlx::query_as::<_, (i16, i64, i64, Option<f64>, Option<f64>)>(
+            r#"
+            SELECT
+                level,
+                COUNT(*) AS exits,
+                COUNT(*) FILTER (WHERE exit_reason = 'Culled') AS culled,
+                AVG(survival_seconds)::DOUBLE PRECISION AS average_survival_seconds,
+                AVG(scans_survived)::DOUBLE PRECISION AS average_scans_survived
+            FROM positions
+            WHERE exit_reason IS NOT NULL AND exit_reason <> 'Superseded' AND level > 0
+            GROUP BY level
+            ORDER BY level
+            "#,
+        )
+        .fetch_all(&mut *self.conn().await?)
+        .await
+        .map_err(InfraError::Database)?;
+
+        rows.into_iter()
+            .map(|(level, exits, culled, survival, scans)| {
+                let level = Level::try_from(level as u8)
+                    .map_err(|e| InfraError::Internal(format!("Invalid level in DB: {e}")))?;
+                let (exits, culled) = (exits.max(0) as u32, culled.max(0) as u32);
+                Ok(LevelSurvival {
+                    level,
+                    exits,
+                    culled,
+                    cull_rate: if exits == 0 {
+                        0.0
+                    } else {
+                        f64::from(culled) / f64::from(exits)
+                    },
+                    average_survival_seconds: survival,
+                    average_scans_survived: scans,
+                })
+            })
+            .collect()
+    }
+
+    #[instrument(skip(self))]
+    async fn get_exit_streak_distribution(&self) -> Result<Vec<ExitStreakCount>> {
+        let _timer = obs::store_timer("get_exit_streak_distribution");
+        let rows = sqlx::query_as::<_, (i16, i32, i64)>(
+            r#"
+            SELECT level, ghost_streak, COUNT(*)
+            FROM positions
+            WHERE exit_reason IS NOT NULL AND exit_reason <> 'Superseded' AND level > 0
+            GROUP BY level, ghost_streak
+            ORDER BY level, ghost_streak
+            "#,
+        )
+        .fetch_all(&mut *self.conn().await?)
+        .await
+        .map_err(InfraError::Database)?;
+
+        rows.into_iter()
+            .map(|(level, streak, exits)| {
+                Ok(ExitStreakCount {
+                    level: Level::try_from(level as u8)
+                        .map_err(|e| InfraError::Internal(format!("Invalid level in DB: {e}")))?,
+                    ghost_streak: GhostStreak::new(streak).map_err(|e| {
+                        InfraError::Internal(format!("Invalid ghost streak in DB: {e}"))
+                    })?,
+                    exits: exits.max(0) as u32,
+                })
+            })
+            .collect()
+    }
+
+    #[instrument(skip(self, scans), fields(count = scans.len()))]
+    async fn record_level_scans(&self, scans: &[LevelScanStats]) -> Result<()> {
+        let _timer = obs::store_timer("record_level_scans");
+        if scans.is_empty() {
+            return Ok(());
+        }
+        let mut tx = self.transaction().await?;
+
+        for scan in scans {
+            sqlx::query(
+                r#"
+                INSERT INTO level_scan_stats (
+                    scanned_at, scan_id, level, deaths, survivors, total_staked, block_number
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7)
+                ON CONFLICT (scanned_at, scan_id) DO NOTHING
+                "#,
+            )
+            .bind(scan.scanned_at)
+            .bind(&scan.scan_id)
+            .bind(i16::from(scan.level))
+            .bind(scan.deaths as i32)
+            .bind(scan.survivors as i32)
+            .bind(scan.total_staked.to_bigdecimal())
+            .bind(scan.block_number.map(|block| block.value() as i64))
+            .execute(&mut *tx)
+            .await
+            .map_err(InfraError::Database)?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    #[allow(clippy::cast_precision_loss)] // Bucket widths in seconds fit in f64's mantissa
+    async fn get_level_history(
+        &self,
+        level: Level,
+        from: chrono::DateTime<chrono::Utc>,
+        to: chrono::DateTime<chrono::Utc>,
+        bucket: HistoryBucket,
+    ) -> Result<Vec<LevelHistoryPoint>> {
+        let _timer = obs::store_timer("get_level_history");
+        let rows = sqlx::query_as::<
+            _,
+            (
+                chrono::DateTime<chrono::Utc>,
+                i64,
+                i64,
+                i64,
+                i32,
+                sqlx::types::BigDecimal,
+            ),
+        >(
+            r#"
+            SELECT
+                time_bucket(make_interval(secs => $4), scanned_at) AS bucket,
+                COUNT(*),
+                SUM(deaths)::BIGINT,
+                SUM(deaths + survivors)::BIGINT,
+                last(survivors, scanned_at),
+                last(total_staked, scanned_at)
+            FROM level_scan_stats
+            WHERE level = $1 AND scanned_at >= $2 AND scanned_at < $3
+            GROUP BY bucket
+            ORDER BY bucket
+            "#,
+        )
+        .bind(i16::from(level))
+        .bind(from)
+        .bind(to)
+        .bind(bucket.duration().num_seconds() as f64)
+        .fetch_all(&mut *self.conn().await?)
+        .await
+        .map_err(InfraError::Database)?;
+
+        let points = rows
+            .into_iter()
+            .map(|(start, scans, deaths, scanned, survivors, staked)| {
+                LevelHistoryPoint::from_totals(
+                    start,
+                    scans.max(0) as u32,
+                    deaths.max(0) as u32,
+                    scanned.max(0) as u64,
+                    (survivors.max(0) as u32, TokenAmount::from_bigdecimal(&staked)),
+                )
+            })
+            .collect();
+        Ok(LevelHistoryPoint::fill_gaps(points, from, to, bucket))
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// LEADERBOARD STORE IMPLEMENTATION
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Ghost streak of each alive position.
+const GHOST_STREAK_LEADERBOARD: &str = r#"
+    SELECT
+        ROW_NUMBER() OVER (ORDER BY ghost_streak DESC, user_address ASC) AS rank,
+        user_address,
+        ghost_streak::NUMERIC AS score,
+        level,
+        NULL::BIGINT AS extractions
+    FROM positions
+    WHERE is_alive = TRUE
+    ORDER BY ghost_streak DESC, user_address ASC
+    LIMIT $1
+"#;
+
+/// Effective stake of each alive position.
+const EFFECTIVE_STAKE_LEADERBOARD: &str = r#"
+    SELECT
+        ROW_NUMBER() OVER (ORDER BY effective_stake DESC, user_address ASC) AS rank,
+        user_address,
+        effective_stake AS score,
+        level,
+        NULL::BIGINT AS extractions
+    FROM positions
+    WHERE is_alive = TRUE
+    ORDER BY effective_stake DESC, user_address ASC
+    LIMIT $1
+"#;
+
+/// Sum of extracted principal across each wallet's positions.
+const TOTAL_EXTRACTED_LEADERBOARD: &str = r#"
+    SELECT
+        ROW_NUMBER() OVER (ORDER BY SUM(extracted_amount) DESC, user_address ASC) AS rank,
+        user_address,
+        SUM(extracted_amount) AS score,
+        NULL::SMALLINT AS level,
+        COUNT(*) AS extractions
+    FROM positions
+    WHERE is_extracted = TRUE AND extracted_amount IS NOT NULL
+    GROUP BY user_address
+    ORDER BY score DESC, user_address ASC
+    LIMIT $1
+"#;
+
+/// Largest single extraction of each wallet.
+const BIGGEST_EXTRACTION_LEADERBOARD: &str = r#"
+    SELECT
+        ROW_NUMBER() OVER (ORDER BY extracted_amount DESC, user_address ASC) AS rank,
+        user_address,
+        extracted_amount AS score,
+        level,
+        NULL::BIGINT AS extractions
+    FROM (
+        SELECT DISTINCT ON (user_address) user_address, extracted_amount, level
+        FROM positions
+        WHERE is_extracted = TRUE AND extracted_amount IS NOT NULL
+        ORDER BY user_address, extracted_amount DESC
+    ) best
+    ORDER BY extracted_amount DESC, user_address ASC
+    LIMIT $1
+"#;
+
+/// Sum of cascade shares received across each wallet's positions.
+const CASCADE_EARNINGS_LEADERBOARD: &str = r#"
+    SELECT
+        ROW_NUMBER() OVER (ORDER BY SUM(amount) DESC, user_address ASC) AS rank,
+        user_address,
+        SUM(amount) AS score,
+        NULL::SMALLINT AS level,
+        NULL::BIGINT AS extractions
+    FROM cascade_rewards
+    GROUP BY user_address
+    ORDER BY score DESC, user_address ASC
+    LIMIT $1
+"#;
+
+/// Sum of claimed winnings across each wallet's bets in resolved rounds.
+const DEAD_POOL_WINNERS_LEADERBOARD: &str = r#"
+    SELECT
+        ROW_NUMBER() OVER (ORDER BY SUM(b.winnings) DESC, b.user_address ASC) AS rank,
+        b.user_address,
+        SUM(b.winnings) AS score,
+        NULL::SMALLINT AS level,
+        NULL::BIGINT AS extractions
+    FROM bets b
+    JOIN rounds r ON r.id = b.round_id
+    WHERE r.is_resolved = TRUE AND b.winnings IS NOT NULL
+    GROUP BY b.user_address
+    ORDER BY score DESC, b.user_address ASC
+    LIMIT $1
+"#;
+
+/// Database row for leaderboard queries.
+#[derive(Debug, FromRow)]
+struct LeaderboardRow {
+    rank: i64,
+    user_address: Vec<u8>,
+    score: sqlx::types::BigDecimal,
+    level: Option<i16>,
+    extractions: Option<i64>,
+}
+
+impl LeaderboardRow {
+    fn into_entry(
+        self,
+        leaderboard: LeaderboardType,
+    ) -> std::result::Result<LeaderboardEntry, InfraError> {
+        // Streaks are plain counters; every other score is a wei amount
+        let score = if leaderboard == LeaderboardType::GhostStreak {
+            TokenAmount::new(self.score).map_err(|e| {
+                InfraError::Internal(format!("Invalid leaderboard score in DB: {e}"))
+            })?
+        } else {
+            TokenAmount::from_bigdecimal(&self.score)
+        };
+
+        let metadata = match (self.level, self.extractions) {
+            (Some(level), _) => Some(serde_json::json!({ "level": level })),
+            (None, Some(extractions)) => Some(serde_json::json!({ "extractions": extractions })),
+            (None, None) => None,
+        };
+
+        Ok(LeaderboardEntry {
+            rank: self.rank.max(0) as u32,
+            user_address: EthAddress::new(
+                self.user_address
+                    .try_into()
+                    .map_err(|_| InfraError::Internal("Invalid address length in DB".into()))?,
+            ),
+            score,
+            metadata,
+        })
+    }
+}
+
+#[async_trait]
+impl LeaderboardStore for PostgresStore {
+    #[instrument(skip(self))]
+    async fn get_leaderboard(
+        &self,
+        leaderboard: LeaderboardType,
+        limit: u32,
+    ) -> Result<Vec<LeaderboardEntry>> {
+        let _timer = obs::store_timer("get_leaderboard");
+        let query = match leaderboard {
+            LeaderboardType::GhostStreak => GHOST_STREAK_LEADERBOARD,
+            LeaderboardType::TotalExtracted => TOTAL_EXTRACTED_LEADERBOARD,
+            LeaderboardType::BiggestExtraction => BIGGEST_EXTRACTION_LEADERBOARD,
+            LeaderboardType::CascadeEarnings => CASCADE_EARNINGS_LEADERBOARD,
+            LeaderboardType::EffectiveStake => EFFECTIVE_STAKE_LEADERBOARD,
+            LeaderboardType::DeadPoolWinners => DEAD_POOL_WINNERS_LEADERBOARD,
+        };
+
+        let rows = sqlx::query_as::<_, LeaderboardRow>(query)
+            .bind(limit as i64)
+            .fetch_all(&mut *self.conn().await?)
+            .await
+            .map_err(InfraError::Database)?;
+
+        rows.into_iter()
+            .map(|row| row.into_entry(leaderboard).map_err(Into::into))
+            .collect()
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TOKEN FLOW STORE IMPLEMENTATION
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[async_trait]
+impl TokenFlowStore for PostgresStore {
+    #[instrument(skip(self, transfer), fields(block = transfer.block_number.value()))]
+    async fn record_transfer(&self, transfer: &TokenTransfer) -> Result<()> {
+        let _timer = obs::store_timer("record_transfer");
+        if let Some(batch) = &self.batch {
+            batch.buffer(|rows| rows.transfers.push(transfer.clone())).await;
+            return Ok(());
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO token_transfers (
+                tx_hash, log_index, from_address, to_address, amount, block_number, timestamp
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (timestamp, tx_hash, log_index) DO NOTHING
+            "#,
+        )
+        .bind(transfer.tx_hash.as_slice())
+        .bind(transfer.log_index as i32)
+        .bind(transfer.from_address.as_bytes())
+        .bind(transfer.to_address.as_bytes())
+        .bind(transfer.amount.to_bigdecimal())
+        .bind(transfer.block_number.value() as i64)
+        .bind(transfer.timestamp)
+        .execute(&mut *self.conn().await?)
+        .await
+        .map_err(InfraError::Database)?;
+
+        Ok(())
+    }
+
+    #[instrument(skip(self, delta), fields(addresses = delta.addresses.len()))]
+    async fn record_token_flows(
+        &self,
+        bucket: chrono::DateTime<chrono::Utc>,
+        delta: &TokenFlowDelta,
+    ) -> Result<()> {
+        let _timer = obs::store_timer("record_token_flows");
+        let mut tx = self.transaction().await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO token_flow_hourly (
+                bucket, burned, tax_burned, tax_collected, volume, transfer_count
+            )
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (bucket) DO UPDATE SET
+                burned = token_flow_hourly.burned + EXCLUDED.burned,
+                tax_burned = token_flow_hourly.tax_burned + EXCLUDED.tax_burned,
+                tax_collected = token_flow_hourly.tax_collected + EXCLUDED.tax_collected,
+                volume = token_flow_hourly.volume + EXCLUDED.volume,
+                transfer_count = token_flow_hourly.transfer_count + EXCLUDED.transfer_count
+            "#,
+        )
+        .bind(bucket)
+        .bind(amount_or_zero(delta.burned.as_ref()))
+        .bind(amount_or_zero(delta.tax_burned.as_ref()))
+        .bind(amount_or_zero(delta.tax_collected.as_ref()))
+        .bind(amount_or_zero(delta.volume.as_ref()))
+        .bind(delta.transfers as i64)
+        .execute(&mut *tx)
+        .await
+        .map_err(InfraError::Database)?;
+
+        for (address, flow) in &delta.addresses {
+            sqlx::query(
+                r#"
+                INSERT INTO address_flow_hourly (
+                    address, bucket, inflow, outflow, transfers_in, transfers_out
+                )
+                VALUES ($1, $2, $3, $4, $5, $6)
+                ON CONFLICT (address, bucket) DO UPDATE SET
+                    inflow = address_flow_hourly.inflow + EXCLUDED.inflow,
+                    outflow = address_flow_hourly.outflow + EXCLUDED.outflow,
+                    transfers_in = address_flow_hourly.transfers_in + EXCLUDED.transfers_in,
+                    transfers_out = address_flow_hourly.transfers_out + EXCLUDED.transfers_out
+                "#,
+            )
+            .bind(address.as_bytes())
+            .bind(bucket)
+            .bind(flow.inflow.to_bigdecimal())
+            .bind(flow.outflow.to_bigdecimal())
+            .bind(flow.transfers_in as i64)
+            .bind(flow.transfers_out as i64)
+            .execute(&mut *tx)
+            .await
+            .map_err(InfraError::Database)?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn get_burn_rate(&self, window: std::time::Duration) -> Result<BurnRate> {
+        let _timer = obs::store_timer("get_burn_rate");
+        let (burned, tax_burned, tax_collected): (
+            sqlx::types::BigDecimal,
+            sqlx::types::BigDecimal,
+            sqlx::types::BigDecimal,
+        ) = sqlx::query_as(
+            r#"
+            SELECT
+                COALESCE(SUM(burned), 0),
+                COALESCE(SUM(tax_burned), 0),
+                COALESCE(SUM(tax_collected), 0)
+            FROM token_flow_hourly
+            WHERE bucket >= date_trunc('hour', NOW() - make_interval(secs => $1))
+            "#,
+        )
+        .bind(window.as_secs_f64())
+        .fetch_one(&mut *self.conn().await?)
+        .await
+        .map_err(InfraError::Database)?;
+
+        Ok(BurnRate::new(
+            window,
+            TokenAmount::from_bigdecimal(&burned),
+            TokenAmount::from_bigdecimal(&tax_burned),
+            TokenAmount::from_bigdecimal(&tax_collected),
+        ))
+    }
+
+    #[instrument(skip(self), fields(address = %address))]
+    async fn get_address_flows(
+        &self,
+        address: &EthAddress,
+        window: std::time::Duration,
+    ) -> Result<AddressFlows> {
+        let _timer = obs::store_timer("get_address_flows");
+        let (inflow, outflow, transfers_in, transfers_out): (
+            sqlx::types::BigDecimal,
+            sqlx::types::BigDecimal,
+            i64,
+            i64,
+        ) = sqlx::query_as(
+            r#"
+            SELECT
+                COALESCE(SUM(inflow), 0),
+                COALESCE(SUM(outflow), 0),
+                COALESCE(SUM(transfers_in), 0)::BIGINT,
+                COALESCE(SUM(transfers_out), 0)::BIGINT
+            FROM address_flow_hourly
+            WHERE address = $1
+              AND bucket >= date_trunc('hour', NOW() - make_interval(secs => $2))
+            "#,
+        )
+        .bind(address.as_bytes())
+        .bind(window.as_secs_f64())
+        .fetch_one(&mut *self.conn().await?)
+        .await
+        .map_err(InfraError::Database)?;
+
+        Ok(AddressFlows {
+            address: *address,
+            window_secs: window.as_secs(),
+            inflow: TokenAmount::from_bigdecimal(&inflow),
+            outflow: TokenAmount::from_bigdecimal(&outflow),
+            transfers_in: transfers_in.max(0) as u64,
+            transfers_out: transfers_out.max(0) as u64,
+        })
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// HOLDER STORE IMPLEMENTATION
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Database row for holder balances.
+#[derive(Debug, FromRow)]
+struct HolderRow {
+    address: Vec<u8>,
+    balance: sqlx::types::BigDecimal,
+    first_seen: chrono::DateTime<chrono::Utc>,
+    last_active: chrono::DateTime<chrono::Utc>,
+    last_block: i64,
+    last_log_index: i32,
+    needs_reconciliation: bool,
+    reconciled_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl TryFrom<HolderRow> for HolderBalance {
+    type Error = InfraError;
+
+    fn try_from(row: HolderRow) -> std::result::Result<Self, Self::Error> {
+        Ok(HolderBalance {
+            address: EthAddress::new(
+                row.address
+                    .try_into()
+                    .map_err(|_| InfraError::Internal("Invalid address length in DB".into()))?,
+            ),
+            balance: wei_from_db(&row.balance)?,
+            first_seen: row.first_seen,
+            last_active: row.last_active,
+            last_block: BlockNumber::new(row.last_block as u64),
+            last_log_index: row.last_log_index as u64,
+            needs_reconciliation: row.needs_reconciliation,
+            reconciled_at: row.reconciled_at,
+        })
+    }
+}
+
+const HOLDER_COLUMNS: &str = "address, balance, first_seen, last_active, last_block, \
+    last_log_index, needs_reconciliation, reconciled_at";
+
+/// Wei as stored in a `NUMERIC(78, 0)` column.
+fn wei_to_db(wei: U256) -> sqlx::types::BigDecimal {
+    // U256 always formats as a plain integer
+    wei.to_string().parse().unwrap_or_default()
+}
+
+/// Wei read from a `NUMERIC(78, 0)` column.
+fn wei_from_db(value: &sqlx::types::BigDecimal) -> std::result::Result<U256, InfraError> {
+    value
+        .with_scale(0)
+        .to_plain_string()
+        .parse()
+        .map_err(|e| InfraError::Internal(format!("Invalid wei amount in DB: {e}")))
+}
+
+#[async_trait]
+impl HolderStore for PostgresStore {
+    #[instrument(skip(self), fields(address = %address))]
+    async fn get_holder(&self, address: &EthAddress) -> Result<Option<HolderBalance>> {
+        let _timer = obs::store_timer("get_holder");
+        let row = sqlx::query_as::<_, HolderRow>(&format!(
+            "SELECT {HOLDER_COLUMNS} FROM holder_balances WHERE address = $1"
+        ))
+        .bind(address.as_bytes())
+        .fetch_optional(&mut *self.conn().await?)
+        .await
+        .map_err(InfraError::Database)?;
+
+        row.map(HolderBalance::try_from).transpose().map_err(Into::into)
+    }
+
+    #[instrument(skip(self, holder), fields(address = %holder.address))]
+    async fn save_holder(&self, holder: &HolderBalance) -> Result<()> {
+        let _timer = obs::store_timer("save_holder");
+        sqlx::query(
+            r#"
+            INSERT INTO holder_balances (
+                address, balance, first_seen, last_active, last_block, last_log_index,
+                needs_reconciliation, reconciled_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            ON CONFLICT (address) DO UPDATE SET
+                balance = EXCLUDED.balance,
+                first_seen = EXCLUDED.first_seen,
+                last_active = EXCLUDED.last_active,
+                last_block = EXCLUDED.last_block,
+                last_log_index = EXCLUDED.last_log_index,
+                needs_reconciliation = EXCLUDED.needs_reconciliation,
+                reconciled_at = EXCLUDED.reconciled_at
+            "#,
+        )
+        .bind(holder.address.as_bytes())
+        .bind(wei_to_db(holder.balance))
+        .bind(holder.first_seen)
+        .bind(holder.last_active)
+        .bind(holder.last_block.value() as i64)
+        .bind(holder.last_log_index as i32)
+        .bind(holder.needs_reconciliation)
+        .bind(holder.reconciled_at)
+        .execute(&mut *self.conn().await?)
+        .await
+        .map_err(InfraError::Database)?;
+
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn get_top_holders(&self, sort: HolderSort, limit: u32) -> Result<Vec<HolderBalance>> {
+        let _timer = obs::store_timer("get_top_holders");
+        let order = match sort {
+            HolderSort::Balance => "balance DESC",
+            HolderSort::FirstSeen => "first_seen DESC",
+            HolderSort::LastActive => "last_active DESC",
+        };
+        let rows = sqlx::query_as::<_, HolderRow>(&format!(
+            "SELECT {HOLDER_COLUMNS} FROM holder_balances WHERE balance > 0 \
+             ORDER BY {order}, address LIMIT $1"
+        ))
+        .bind(limit as i64)
+        .fetch_all(&mut *self.conn().await?)
+        .await
+        .map_err(InfraError::Database)?;
+
+        rows.into_iter()
+            .map(|r| HolderBalance::try_from(r).map_err(Into::into))
+            .collect()
+    }
+
+    #[instrument(skip(self))]
+    async fn count_holders(&self) -> Result<u64> {
+        let _timer = obs::store_timer("count_holders");
+        let count: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM holder_balances WHERE balance > 0")
+                .fetch_one(&mut *self.conn().await?)
+                .await
+                .map_err(InfraError::Database)?;
+
+        Ok(count.max(0) as u64)
+    }
+
+    #[instrument(skip(self))]
+    async fn get_holders_to_reconcile(&self, limit: u32) -> Result<Vec<HolderBalance>> {
+        let _timer = obs::store_timer("get_holders_to_reconcile");
+        let rows = sqlx::query_as::<_, HolderRow>(&format!(
+            "SELECT {HOLDER_COLUMNS} FROM holder_balances \
+             ORDER BY needs_reconciliation DESC, reconciled_at NULLS FIRST LIMIT $1"
+        ))
+        .bind(limit as i64)
+        .fetch_all(&mut *self.conn().await?)
+        .await
+        .map_err(InfraError::Database)?;
+
+        rows.into_iter()
+            .map(|r| HolderBalance::try_from(r).map_err(Into::into))
+            .collect()
+    }
+
+    #[instrument(skip(self, holder), fields(address = %holder.address))]
+    async fn reconcile_holder(
+        &self,
+        holder: &HolderBalance,
+        balance: U256,
+        at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<bool> {
+        let _timer = obs::store_timer("reconcile_holder");
+        let result = sqlx::query(
+            r#"
+            UPDATE holder_balances
+            SET balance = $4, needs_reconciliation = FALSE, reconciled_at = $5
+            WHERE address = $1 AND last_block = $2 AND last_log_index = $3
+            "#,
+        )
+        .bind(holder.address.as_bytes())
+        .bind(holder.last_block.value() as i64)
+        .bind(holder.last_log_index as i32)
+        .bind(wei_to_db(balance))
+        .bind(at)
+        .execute(&mut *self.conn().await?)
+        .await
+        .map_err(InfraError::Database)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// BOOST STORE IMPLEMENTATION
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Database row for boosts.
+#[derive(Debug, FromRow)]
+struct BoostRow {
+    id: Uuid,
+    position_id: Uuid,
+    user_address: Vec<u8>,
+    boost_type: i16,
+    value_bps: i16,
+    expiry: chrono::DateTime<chrono::Utc>,
+    block_number: i64,
+    log_index: i32,
+    created_at: chrono::DateTime<chrono::Utc>,
+    expired_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl TryFrom<BoostRow> for Boost {
+    type Error = InfraError;
+
+    fn try_from(row: BoostRow) -> std::result::Result<Self, Self::Error> {
+        Ok(Boost {
+            id: row.id,
+            position_id: row.position_id,
+            user_address: EthAddress::new(
+                row.user_address
+                    .try_into()
+                    .map_err(|_| InfraError::Internal("Invalid address length in DB".into()))?,
+            ),
+            boost_type: BoostType::try_from(row.boost_type as u8)
+                .map_err(|e| InfraError::Internal(format!("Invalid boost type in DB: {e}")))?,
+            value_bps: row.value_bps,
+            expiry: row.expiry,
+            block_number: BlockNumber::new(row.block_number as u64),
+            log_index: row.log_index as u64,
+            created_at: row.created_at,
+            expired_at: row.expired_at,
+        })
+    }
+}
+
+/// Database row for boosts not recorded as expired, with the exit of their
+/// position.
+#[derive(Debug, FromRow)]
+struct LapsedBoostRow {
+    #[sqlx(flatten)]
+    boost: BoostRow,
+    position_closed_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+const BOOST_COLUMNS: &str = "b.id, b.position_id, b.user_address, b.boost_type, b.value_bps, \
+    b.expiry, b.block_number, b.log_index, b.created_at, b.expired_at";
+
+/// Condition on `b` joined with its position `p` for an active boost at `$1`.
+const ACTIVE_BOOST: &str =
+    "b.expired_at IS NULL AND b.expiry > $1 AND p.is_alive AND NOT p.is_extracted";
+
+/// Database row for a position whose effective stake changed.
+#[derive(Debug, FromRow)]
+struct EffectiveStakeRow {
+    id: Uuid,
+    level: i16,
+    previous: sqlx::types::BigDecimal,
+    current: sqlx::types::BigDecimal,
+}
+
+impl TryFrom<EffectiveStakeRow> for EffectiveStakeChange {
+    type Error = InfraError;
+
+    fn try_from(row: EffectiveStakeRow) -> std::result::Result<Self, Self::Error> {
+        Ok(EffectiveStakeChange {
+            position_id: row.id,
+            level: Level::try_from(row.level as u8)
+                .map_err(|e| InfraError::Internal(format!("Invalid level in DB: {e}")))?,
+            previous: TokenAmount::from_bigdecimal(&row.previous),
+            current: TokenAmount::from_bigdecimal(&row.current),
+        })
+    }
+}
+
+/// Set the effective stake of the active positions among `ids`, or of all
+/// of them, from their yield multipliers active at `now`, as
+/// [`Position::apply_boosts`] does. Returns the positions that changed.
+async fn reweigh_positions<'e, E: PgExecutor<'e>>(
+    executor: E,
+    ids: Option<&[Uuid]>,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Result<Vec<EffectiveStakeChange>> {
+    // Summed onto the base weight, then rounded half up to whole wei
+    let base = Position::BASE_STAKE_WEIGHT_BPS;
+    let rows = sqlx::query_as::<_, EffectiveStakeRow>(&format!(
+        r#"
+        WITH weighed AS (
+            SELECT p.id, p.effective_stake AS previous,
+                   div(p.amount * ({base} + w.bps) + {half}, {base}) AS current
+            FROM positions p
+            CROSS JOIN LATERAL (
+                SELECT COALESCE(SUM(GREATEST(b.value_bps, 0)), 0) AS bps
+                FROM boosts b
+                WHERE b.position_id = p.id AND b.boost_type = $3
+                  AND b.expired_at IS NULL AND b.expiry > $2
+            ) w
+            WHERE p.is_alive AND NOT p.is_extracted
+              AND ($1::UUID[] IS NULL OR p.id = ANY($1))
+        )
+        UPDATE positions p SET effective_stake = weighed.current
+        FROM weighed
+        WHERE p.id = weighed.id AND p.effective_stake <> weighed.current
+        RETURNING p.id, p.level, weighed.previous, weighed.current
+        "#,
+        half = base / 2,
+    ))
+    .bind(ids)
+    .bind(now)
+    .bind(i16::from(BoostType::YieldMultiplier))
+    .fetch_all(executor)
+    .await
+    .map_err(InfraError::Database)?;
+
+    rows.into_iter()
+        .map(|r| EffectiveStakeChange::try_from(r).map_err(Into::into))
+        .collect()
+}
+
+#[async_trait]
+impl BoostStore for PostgresStore {
+    #[instrument(skip(self, boost), fields(user = %boost.user_address))]
+    async fn save_boost(&self, boost: &Boost) -> Result<()> {
+        let _timer = obs::store_timer("save_boost");
+        sqlx::query(
+            r#"
+            INSERT INTO boosts (
+                id, position_id, user_address, boost_type, value_bps, expiry,
+                block_number, log_index, created_at, expired_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            ON CONFLICT (block_number, log_index) DO NOTHING
+            "#,
+        )
+        .bind(boost.id)
+        .bind(boost.position_id)
+        .bind(boost.user_address.as_bytes())
+        .bind(i16::from(boost.boost_type))
+        .bind(boost.value_bps)
+        .bind(boost.expiry)
+        .bind(boost.block_number.value() as i64)
+        .bind(boost.log_index as i32)
+        .bind(boost.created_at)
+        .bind(boost.expired_at)
+        .execute(&mut *self.conn().await?)
+        .await
+        .map_err(InfraError::Database)?;
+
+        Ok(())
+    }
+
+    #[instrument(skip(self), fields(address = %address))]
+    async fn get_active_boosts(
+        &self,
+        address: &EthAddress,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<Boost>> {
+        let _timer = obs::store_timer("get_active_boosts");
+        let rows = sqlx::query_as::<_, BoostRow>(&format!(
+            "SELECT {BOOST_COLUMNS} FROM boosts b JOIN positions p ON p.id = b.position_id \
+             WHERE b.user_address = $2 AND {ACTIVE_BOOST} ORDER BY b.expiry, b.id"
+        ))
+        .bind(now)
+        .bind(address.as_bytes())
+        .fetch_all(&mut *self.conn().await?)
+        .await
+        .map_err(InfraError::Database)?;
+
+        rows.into_iter()
+            .map(|r| Boost::try_from(r).map_err(Into::into))
+            .collect()
+    }
+
+    #[instrument(skip(self))]
+    async fn get_expiring_boosts(
+        &self,
+        now: chrono::DateTime<chrono::Utc>,
+        before: chrono::DateTime<chrono::Utc>,
+        limit: u32,
+    ) -> Result<Vec<Boost>> {
+        let _timer = obs::store_timer("get_expiring_boosts");
+        let rows = sqlx::query_as::<_, BoostRow>(&format!(
+            "SELECT {BOOST_COLUMNS} FROM boosts b JOIN positions p ON p.id = b.position_id \
+             WHERE b.expiry < $2 AND {ACTIVE_BOOST} ORDER BY b.expiry, b.id LIMIT $3"
+        ))
+        .bind(now)
+        .bind(before)
+        .bind(limit as i64)
+        .fetch_all(&mut *self.conn().await?)
+        .await
+        .map_err(InfraError::Database)?;
+
+        rows.into_iter()
+            .map(|r| Boost::try_from(r).map_err(Into::into))
+            .collect()
+    }
+
+    #[instrument(skip(self))]
+    async fn get_lapsed_boosts(
+        &self,
+        now: chrono::DateTime<chrono::Utc>,
+        limit: u32,
+    ) -> Result<Vec<LapsedBoost>> {
+        let _timer = obs::store_timer("get_lapsed_boosts");
+        // A closed position without an exit time ends its boosts now
+        let rows = sqlx::query_as::<_, LapsedBoostRow>(&format!(
+            r#"
+            SELECT {BOOST_COLUMNS}, closed.at AS position_closed_at
+            FROM boosts b
+            JOIN positions p ON p.id = b.position_id
+            CROSS JOIN LATERAL (
+                SELECT CASE WHEN p.is_alive AND NOT p.is_extracted THEN NULL
+                    ELSE COALESCE(p.exit_timestamp, $1) END AS at
+            ) closed
+            WHERE b.expired_at IS NULL AND (b.expiry <= $1 OR closed.at IS NOT NULL)
+            ORDER BY LEAST(b.expiry, COALESCE(closed.at, b.expiry)), b.id
+            LIMIT $2
+            "#
+        ))
+        .bind(now)
+        .bind(limit as i64)
+        .fetch_all(&mut *self.conn().await?)
+        .await
+        .map_err(InfraError::Database)?;
+
+        rows.into_iter()
+            .map(|row| {
+                Ok(LapsedBoost {
+                    boost: Boost::try_from(row.boost)?,
+                    position_closed_at: row.position_closed_at,
+                })
+            })
+            .collect()
+    }
+
+    #[instrument(skip(self, expired), fields(count = expired.len()))]
+    async fn expire_boosts(
+        &self,
+        expired: &[(Uuid, chrono::DateTime<chrono::Utc>)],
+    ) -> Result<u64> {
+        let _timer = obs::store_timer("expire_boosts");
+        let (ids, ended): (Vec<Uuid>, Vec<chrono::DateTime<chrono::Utc>>) =
+            expired.iter().copied().unzip();
+        let result = sqlx::query(
+            r#"
+            UPDATE boosts b SET expired_at = expired.ended_at
+            FROM UNNEST($1::UUID[], $2::TIMESTAMPTZ[]) AS expired(id, ended_at)
+            WHERE b.id = expired.id AND b.expired_at IS NULL
+            "#,
+        )
+        .bind(&ids)
+        .bind(&ended)
+        .execute(&mut *self.conn().await?)
+        .await
+        .map_err(InfraError::Database)?;
+
+        Ok(result.rows_affected())
+    }
+
+    #[instrument(skip(self, positions), fields(count = positions.len()))]
+    async fn reweigh_positions(
+        &self,
+        positions: &[Uuid],
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<EffectiveStakeChange>> {
+        let _timer = obs::store_timer("reweigh_positions");
+        reweigh_positions(&mut *self.conn().await?, Some(positions), now).await
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// EVENT OUTBOX STORE IMPLEMENTATION
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[derive(Debug, FromRow)]
+struct OutboxRow {
+    id: i64,
+    topic: String,
+    aggregate: String,
+    event_type: String,
+    payload: Vec<u8>,
+    block_number: i64,
+    attempts: i32,
+    created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<OutboxRow> for OutboxRecord {
+    fn from(row: OutboxRow) -> Self {
+        Self {
+            id: row.id,
+            event: OutboxEvent {
+                topic: row.topic,
+                aggregate: row.aggregate,
+                event_type: row.event_type,
+                payload: row.payload,
+                block_number: BlockNumber::new(row.block_number as u64),
+            },
+            attempts: row.attempts.max(0) as u32,
+            created_at: row.created_at,
+        }
+    }
+}
+
+/// Insert outbox events on `conn`, in order.
+async fn insert_outbox_events(conn: &mut PgConnection, events: &[OutboxEvent]) -> Result<()> {
+    for event in events {
+        sqlx::query(
+            r#"
+            INSERT INTO event_outbox (topic, aggregate, event_type, payload, block_number)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+        )
+        .bind(&event.topic)
+        .bind(&event.aggregate)
+        .bind(&event.event_type)
+        .bind(&event.payload)
+        .bind(event.block_number.value() as i64)
+        .execute(&mut *conn)
+        .await
+        .map_err(InfraError::Database)?;
+    }
+    Ok(())
+}
+
+#[async_trait]
+impl EventOutboxStore for PostgresStore {
+    #[instrument(skip(self, events), fields(count = events.len()))]
+    async fn enqueue_events(&self, events: &[OutboxEvent]) -> Result<()> {
+        let _timer = obs::store_timer("enqueue_events");
+        let mut tx = self.transaction().await?;
+        insert_outbox_events(&mut tx, events).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    #[instrument(skip(self), fields(limit = limit))]
+    async fn fetch_unpublished(
+        &self,
+        limit: u32,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<OutboxRecord>> {
+        let _timer = obs::store_timer("fetch_unpublished");
+        let rows = sqlx::query_as::<_, OutboxRow>(
+            r#"
+            SELECT o.id, o.topic, o.aggregate, o.event_type, o.payload, o.block_number,
+                   o.attempts, o.created_at
+            FROM event_outbox o
+            WHERE o.published_at IS NULL
+              AND (o.next_attempt_at IS NULL OR o.next_attempt_at <= $1)
+              AND NOT EXISTS (
+                  SELECT 1 FROM event_outbox e
+                  WHERE e.aggregate = o.aggregate
+                    AND e.id < o.id
+                    AND e.published_at IS NULL
+                    AND e.next_attempt_at > $1
+              )
+            ORDER BY o.id
+            LIMIT $2
+            "#,
+        )
+        .bind(now)
+        .bind(i64::from(limit))
+        .fetch_all(&mut *self.conn().await?)
+        .await
+        .map_err(InfraError::Database)?;
+
+        Ok(rows.into_iter().map(OutboxRecord::from).collect())
+    }
+
+    #[instrument(skip(self, ids), fields(count = ids.len()))]
+    async fn mark_published(&self, ids: &[i64], at: chrono::DateTime<chrono::Utc>) -> Result<()> {
+        let _timer = obs::store_timer("mark_published");
+        sqlx::query(
+            "UPDATE event_outbox SET published_at = $2, next_attempt_at = NULL \
+             WHERE id = ANY($1)",
+        )
+        .bind(ids)
+        .bind(at)
+        .execute(&mut *self.conn().await?)
+        .await
+        .map_err(InfraError::Database)?;
+        Ok(())
+    }
+
+    #[instrument(skip(self, error), fields(id = id))]
+    async fn record_publish_failure(
+        &self,
+        id: i64,
+        error: &str,
+        retry_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<()> {
+        let _timer = obs::store_timer("record_publish_failure");
+        sqlx::query(
+            "UPDATE event_outbox SET attempts = attempts + 1, last_error = $2, \
+             next_attempt_at = $3 WHERE id = $1",
+        )
+        .bind(id)
+        .bind(error)
+        .bind(retry_at)
+        .execute(&mut *self.conn().await?)
+        .await
+        .map_err(InfraError::Database)?;
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn purge_published(&self, before: chrono::DateTime<chrono::Utc>) -> Result<u64> {
+        let _timer = obs::store_timer("purge_published");
+        let result = sqlx::query("DELETE FROM event_outbox WHERE published_at < $1")
+            .bind(before)
+            .execute(&mut *self.conn().await?)
+            .await
+            .map_err(InfraError::Database)?;
+        Ok(result.rows_affected())
+    }
+
+    #[instrument(skip(self))]
+    async fn outbox_depth(&self) -> Result<u64> {
+        let _timer = obs::store_timer("outbox_depth");
+        let depth: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM event_outbox WHERE published_at IS NULL")
+                .fetch_one(&mut *self.conn().await?)
+                .await
+                .map_err(InfraError::Database)?;
+        Ok(depth.max(0) as u64)
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// EVENT LOG STORE IMPLEMENTATION
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[derive(Debug, FromRow)]
+struct LoggedEventRow {
+    topic: String,
+    sequence: i64,
+    payload: Vec<u8>,
+    published_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<LoggedEventRow> for LoggedEvent {
+    fn from(row: LoggedEventRow) -> Self {
+        Self {
+            topic: row.topic,
+            sequence: row.sequence.max(0) as u64,
+            payload: row.payload,
+            published_at: row.published_at,
+        }
+    }
+}
+
+#[async_trait]
+impl EventLogStore for PostgresStore {
+    #[instrument(skip(self, messages), fields(count = messages.len()))]
+    async fn log_events(&self, topic: &str, messages: &[IdentifiedMessage]) -> Result<Vec<u64>> {
+        let _timer = obs::store_timer("log_events");
+        let ids: Vec<Vec<u8>> = messages
+            .iter()
+            .map(|message| message.id.to_be_bytes().to_vec())
+            .collect();
+        let mut tx = self.transaction().await?;
+
+        // Lock the topic's counter, so a message logged twice at once gets
+        // one sequence
+        sqlx::query(
+            "INSERT INTO event_log_sequences (topic, last_sequence) VALUES ($1, 0) \
+             ON CONFLICT (topic) DO NOTHING",
+        )
+        .bind(topic)
+        .execute(&mut *tx)
+        .await
+        .map_err(InfraError::Database)?;
+        let last: i64 = sqlx::query_scalar(
+            "SELECT last_sequence FROM event_log_sequences WHERE topic = $1 FOR UPDATE",
+        )
+        .bind(topic)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(InfraError::Database)?;
+
+        let mut known: HashMap<Vec<u8>, i64> = sqlx::query_as(
+            "SELECT message_id, sequence FROM event_log WHERE topic = $1 AND message_id = ANY($2)",
+        )
+        .bind(topic)
+        .bind(&ids)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(InfraError::Database)?
+        .into_iter()
+        .collect();
+
+        let mut next = last;
+        let mut sequences = Vec::with_capacity(messages.len());
+        let mut new_sequences = Vec::new();
+        let mut new_ids = Vec::new();
+        let mut new_payloads = Vec::new();
+        for (message, id) in messages.iter().zip(ids) {
+            let sequence = if let Some(&sequence) = known.get(&id) {
+                sequence
+            } else {
+                next += 1;
+                new_sequences.push(next);
+                new_ids.push(id.clone());
+                new_payloads.push(message.payload.to_vec());
+                known.insert(id, next);
+                next
+            };
+            sequences.push(sequence as u64);
+        }
+
+        if next > last {
+            sqlx::query(
+                r#"
+                INSERT INTO event_log (topic, sequence, message_id, payload)
+                SELECT $1, * FROM UNNEST($2::BIGINT[], $3::BYTEA[], $4::BYTEA[])
+                "#,
+            )
+            .bind(topic)
+            .bind(&new_sequences)
+            .bind(&new_ids)
+            .bind(&new_payloads)
+            .execute(&mut *tx)
+            .await
+            .map_err(InfraError::Database)?;
+            sqlx::query("UPDATE event_log_sequences SET last_sequence = $2 WHERE topic = $1")
+                .bind(topic)
+                .bind(next)
+                .execute(&mut *tx)
+                .await
+                .map_err(InfraError::Database)?;
+        }
+        tx.commit().await?;
+        Ok(sequences)
+    }
+
+    #[instrument(skip(self, sequences), fields(count = sequences.len()))]
+    async fn mark_events_published(
+        &self,
+        topic: &str,
+        sequences: &[u64],
+        at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<()> {
+        let _timer = obs::store_timer("mark_events_published");
+        let sequences: Vec<i64> = sequences.iter().map(|&sequence| sequence as i64).collect();
+        sqlx::query(
+            "UPDATE event_log SET published_at = $3 \
+             WHERE topic = $1 AND sequence = ANY($2) AND published_at IS NULL",
+        )
+        .bind(topic)
+        .bind(&sequences)
+        .bind(at)
+        .execute(&mut *self.conn().await?)
+        .await
+        .map_err(InfraError::Database)?;
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn get_logged_events(
+        &self,
+        topic: &str,
+        from_sequence: u64,
+        limit: u32,
+    ) -> Result<Vec<LoggedEvent>> {
+        let _timer = obs::store_timer("get_logged_events");
+        let rows = sqlx::query_as::<_, LoggedEventRow>(
+            r#"
+            SELECT topic, sequence, payload, published_at
+            FROM event_log
+            WHERE topic = $1 AND sequence >= $2 AND published_at IS NOT NULL
+            ORDER BY sequence
+            LIMIT $3
+            "#,
+        )
+        .bind(topic)
+        .bind(from_sequence as i64)
+        .bind(i64::from(limit))
+        .fetch_all(&mut *self.conn().await?)
+        .await
+        .map_err(InfraError::Database)?;
+        Ok(rows.into_iter().map(LoggedEvent::from).collect())
+    }
+
+    #[instrument(skip(self))]
+    async fn event_log_bounds(&self, topic: &str) -> Result<EventLogBounds> {
+        let _timer = obs::store_timer("event_log_bounds");
+        let (oldest, latest): (Option<i64>, Option<i64>) = sqlx::query_as(
+            r#"
+            SELECT MIN(sequence),
+                   MAX(sequence) FILTER (WHERE published_at IS NOT NULL)
+            FROM event_log
+            WHERE topic = $1
+            "#,
+        )
+        .bind(topic)
+        .fetch_one(&mut *self.conn().await?)
+        .await
+        .map_err(InfraError::Database)?;
+        Ok(EventLogBounds {
+            oldest: oldest.map(|sequence| sequence.max(0) as u64),
+            latest: latest.unwrap_or_default().max(0) as u64,
+        })
+    }
+
+    #[instrument(skip(self))]
+    async fn purge_event_log(
+        &self,
+        before: chrono::DateTime<chrono::Utc>,
+        keep: u64,
+    ) -> Result<u64> {
+        let _timer = obs::store_timer("purge_event_log");
+        // Per topic, cut after the last expired or the last beyond `keep`
+        // published event, whichever is later, but before the latest one
+        let result = sqlx::query(
+            r#"
+            WITH cuts AS (
+                SELECT topic,
+                       LEAST(
+                           GREATEST(
+                               COALESCE(MAX(sequence) FILTER (WHERE published_at < $1), 0),
+                               MAX(sequence) - $2
+                           ),
+                           MAX(sequence) - 1
+                       ) AS cut
+                FROM event_log
+                WHERE published_at IS NOT NULL
+                GROUP BY topic
+            )
+            DELETE FROM event_log e
+            USING cuts c
+            WHERE e.topic = c.topic
+              AND e.sequence <= c.cut
+              AND e.published_at IS NOT NULL
+            "#,
+        )
+        .bind(before)
+        .bind(keep.min(i64::MAX as u64) as i64)
+        .execute(&mut *self.conn().await?)
+        .await
+        .map_err(InfraError::Database)?;
+        Ok(result.rows_affected())
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// RAW LOG STORE IMPLEMENTATION
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Tables derived by each handler, children first.
+///
+/// Handlers missing here keep their state in the cache (or, for the market
+/// handler, in a store that is not implemented yet).
+const DERIVED_TABLES: &[(&str, &[&str])] = &[
+    ("position", &["boosts", "position_history", "positions"]),
+    ("death", &["cascade_rewards", "deaths"]),
+    ("scan", &["scans"]),
+    ("token", &["holder_balances"]),
+];
+
+#[derive(Debug, FromRow)]
+struct RawLogRow {
+    block_number: i64,
+    tx_index: i64,
+    log_index: i64,
+    block_hash: Vec<u8>,
+    tx_hash: Vec<u8>,
+    address: Vec<u8>,
+    topics: Vec<u8>,
+    data: Vec<u8>,
+    timestamp: chrono::DateTime<chrono::Utc>,
+    tx_function: Option<String>,
+    tx_from: Option<Vec<u8>>,
+    chain_id: i64,
+}
+
+impl TryFrom<RawLogRow> for RawLog {
+    type Error = InfraError;
+
+    fn try_from(row: RawLogRow) -> std::result::Result<Self, Self::Error> {
+        let hash = |bytes: &[u8]| {
+            B256::try_from(bytes)
+                .map_err(|_| InfraError::Internal("Invalid hash length in DB".into()))
+        };
+        let address = |bytes: &[u8]| {
+            Address::try_from(bytes)
+                .map_err(|_| InfraError::Internal("Invalid address length in DB".into()))
+        };
+        if !row.topics.len().is_multiple_of(32) {
+            return Err(InfraError::Internal("Invalid topics length in DB".into()));
+        }
+
+        Ok(RawLog {
+            address: address(&row.address)?,
+            chain_id: row.chain_id as u64,
+            topics: row.topics.chunks_exact(32).map(B256::from_slice).collect(),
+            data: row.data.into(),
+            block_number: BlockNumber::new(row.block_number as u64),
+            block_hash: hash(&row.block_hash)?,
+            tx_hash: hash(&row.tx_hash)?,
+            tx_index: row.tx_index as u64,
+            log_index: row.log_index as u64,
+            timestamp: row.timestamp,
+            tx_function: row.tx_function,
+            tx_from: row.tx_from.as_deref().map(address).transpose()?,
+        })
+    }
+}
+
+#[async_trait]
+impl RawLogStore for PostgresStore {
+    #[instrument(skip(self, logs), fields(count = logs.len()))]
+    async fn archive_logs(&self, logs: &[RawLog]) -> Result<()> {
+        let _timer = obs::store_timer("archive_logs");
+        if let Some(batch) = &self.batch {
+            batch.buffer(|rows| rows.raw_logs.extend_from_slice(logs)).await;
+            return Ok(());
+        }
+        batch::insert_raw_logs(&mut *self.conn().await?, logs).await
+    }
+
+    #[instrument(skip(self), fields(from = from.value(), to = to.value()))]
+    async fn get_raw_logs(
+        &self,
+        from: BlockNumber,
+        to: BlockNumber,
+        after: Option<(BlockNumber, u64, u64)>,
+        limit: u32,
+    ) -> Result<Vec<RawLog>> {
+        let _timer = obs::store_timer("get_raw_logs");
+        // Without a cursor, start just before the first log of `from`
+        let (block, tx_index, log_index) = match after {
+            Some((block, tx_index, log_index)) => {
+                (block.value() as i64, tx_index as i64, log_index as i64)
+            }
+            None => (from.value() as i64, -1, -1),
+        };
+        let rows = sqlx::query_as::<_, RawLogRow>(
+            r#"
+            SELECT block_number, tx_index, log_index, block_hash, tx_hash, address,
+                   topics, data, timestamp, tx_function, tx_from, chain_id
+            FROM raw_logs
+            WHERE block_number >= $1 AND block_number <= $2
+              AND (block_number, tx_index, log_index) > ($3, $4, $5)
+            ORDER BY block_number, tx_index, log_index
+            LIMIT $6
+            "#,
+        )
+        .bind(from.value() as i64)
+        .bind(to.value() as i64)
+        .bind(block)
+        .bind(tx_index)
+        .bind(log_index)
+        .bind(i64::from(limit))
+        .fetch_all(&mut *self.flushed_conn().await?)
+        .await
+        .map_err(InfraError::Database)?;
+
+        rows.into_iter()
+            .map(|row| RawLog::try_from(row).map_err(Into::into))
+            .collect()
+    }
+
+    #[instrument(skip(self))]
+    async fn raw_log_range(&self) -> Result<Option<(BlockNumber, BlockNumber)>> {
+        let _timer = obs::store_timer("raw_log_range");
+        let (first, last): (Option<i64>, Option<i64>) =
+            sqlx::query_as("SELECT MIN(block_number), MAX(block_number) FROM raw_logs")
+                .fetch_one(&mut *self.flushed_conn().await?)
+                .await
+                .map_err(InfraError::Database)?;
+
+        Ok(first
+            .zip(last)
+            .map(|(first, last)| (BlockNumber::new(first as u64), BlockNumber::new(last as u64))))
+    }
+
+    #[instrument(skip(self))]
+    async fn get_replay_progress(&self, replay: &str) -> Result<Option<BlockNumber>> {
+        let _timer = obs::store_timer("get_replay_progress");
+        let row: Option<i64> =
+            sqlx::query_scalar("SELECT last_block FROM replay_progress WHERE replay = $1")
+                .bind(replay)
+                .fetch_optional(&mut *self.conn().await?)
+                .await
+                .map_err(InfraError::Database)?;
+
+        Ok(row.map(|block| BlockNumber::new(block as u64)))
+    }
+
+    #[instrument(skip(self), fields(block = %block.value()))]
+    async fn set_replay_progress(&self, replay: &str, block: BlockNumber) -> Result<()> {
+        let _timer = obs::store_timer("set_replay_progress");
+        sqlx::query(
+            r#"
+            INSERT INTO replay_progress (replay, last_block, updated_at)
+            VALUES ($1, $2, NOW())
+            ON CONFLICT (replay) DO UPDATE SET
+                last_block = EXCLUDED.last_block,
+                updated_at = NOW()
+            "#,
+        )
+        .bind(replay)
+        .bind(block.value() as i64)
+        .execute(&mut *self.conn().await?)
+        .await
+        .map_err(InfraError::Database)?;
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn clear_replay_progress(&self, replay: &str) -> Result<()> {
+        let _timer = obs::store_timer("clear_replay_progress");
+        sqlx::query("DELETE FROM replay_progress WHERE replay = $1")
+            .bind(replay)
+            .execute(&mut *self.conn().await?)
+            .await
+            .map_err(InfraError::Database)?;
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn clear_derived(&self, handlers: &[&str]) -> Result<u64> {
+        let _timer = obs::store_timer("clear_derived");
+        let mut tx = self.transaction().await?;
+        let mut deleted = 0;
+        for (_, tables) in DERIVED_TABLES
+            .iter()
+            .filter(|(handler, _)| handlers.contains(handler))
+        {
+            for table in *tables {
+                // Table names come from `DERIVED_TABLES`, never from input
+                let result = sqlx::query(&format!("DELETE FROM {table}"))
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(InfraError::Database)?;
+                deleted += result.rows_affected();
+            }
+        }
+        tx.commit().await?;
+
+        debug!(deleted, "Derived tables cleared");
+        Ok(deleted)
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// QUARANTINE STORE IMPLEMENTATION
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[derive(Debug, FromRow)]
+struct QuarantinedRow {
+    id: i64,
+    #[sqlx(flatten)]
+    log: RawLogRow,
+    handler: String,
+    aggregate: Option<Vec<u8>>,
+    error: String,
+    blocked_by: Option<i64>,
+    attempts: i32,
+    quarantined_at: chrono::DateTime<chrono::Utc>,
+    last_attempt_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl TryFrom<QuarantinedRow> for QuarantinedEvent {
+    type Error = InfraError;
+
+    fn try_from(row: QuarantinedRow) -> std::result::Result<Self, Self::Error> {
+        let aggregate = row
+            .aggregate
+            .as_deref()
+            .map(|bytes| {
+                B256::try_from(bytes)
+                    .map_err(|_| InfraError::Internal("Invalid aggregate length in DB".into()))
+            })
+            .transpose()?;
+
+        Ok(QuarantinedEvent {
+            id: row.id,
+            log: RawLog::try_from(row.log)?,
+            handler: row.handler,
+            aggregate,
+            error: row.error,
+            blocked_by: row.blocked_by,
+            attempts: row.attempts as u32,
+            quarantined_at: row.quarantined_at,
+            last_attempt_at: row.last_attempt_at,
+        })
+    }
+}
+
+const QUARANTINED_COLUMNS: &str = "id, block_number, tx_index, log_index, block_hash, tx_hash, \
+     address, topics, data, timestamp, tx_function, tx_from, chain_id, handler, aggregate, \
+     error, blocked_by, attempts, quarantined_at, last_attempt_at";
+
+// Quarantine writes go straight to the pool: the batch the event failed in
+// is discarded, and the quarantine has to outlive it.
+#[async_trait]
+impl QuarantineStore for PostgresStore {
+    #[instrument(skip(self, log, error), fields(block = %log.block_number.value()))]
+    async fn quarantine_event(
+        &self,
+        log: &RawLog,
+        handler: &str,
+        aggregate: Option<B256>,
+        error: &str,
+        blocked_by: Option<i64>,
+    ) -> Result<i64> {
+        let _timer = obs::store_timer("quarantine_event");
+        let id: i64 = sqlx::query_scalar(
+            r#"
+            INSERT INTO quarantined_events (
+                block_number, tx_index, log_index, block_hash, tx_hash, address,
+                topics, data, timestamp, tx_function, tx_from, chain_id,
+                handler, aggregate, error, blocked_by
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16)
+            ON CONFLICT (block_number, tx_index, log_index) DO UPDATE SET
+                error = EXCLUDED.error,
+                blocked_by = EXCLUDED.blocked_by
+            RETURNING id
+            "#,
+        )
+        .bind(log.block_number.value() as i64)
+        .bind(log.tx_index as i64)
+        .bind(log.log_index as i64)
+        .bind(log.block_hash.as_slice())
+        .bind(log.tx_hash.as_slice())
+        .bind(log.address.as_slice())
+        .bind(log.topics.concat())
+        .bind(log.data.as_ref())
+        .bind(log.timestamp)
+        .bind(log.tx_function.as_deref())
+        .bind(log.tx_from.as_ref().map(|a| a.as_slice()))
+        .bind(log.chain_id as i64)
+        .bind(handler)
+        .bind(aggregate.as_ref().map(B256::as_slice))
+        .bind(error)
+        .bind(blocked_by)
+        .fetch_one(&mut *self.retry.acquire(&self.pool).await?)
+        .await
+        .map_err(InfraError::Database)?;
+
+        Ok(id)
+    }
+
+    #[instrument(skip(self))]
+    async fn list_quarantined(&self) -> Result<Vec<QuarantinedEvent>> {
+        let _timer = obs::store_timer("list_quarantined");
+        let rows = sqlx::query_as::<_, QuarantinedRow>(&format!(
+            "SELECT {QUARANTINED_COLUMNS} FROM quarantined_events \
+             ORDER BY block_number, tx_index, log_index"
+        ))
+        .fetch_all(&mut *self.retry.acquire(&self.pool).await?)
+        .await
+        .map_err(InfraError::Database)?;
+
+        rows.into_iter()
+            .map(|row| QuarantinedEvent::try_from(row).map_err(Into::into))
+            .collect()
+    }
+
+    #[instrument(skip(self))]
+    async fn get_quarantined(&self, id: i64) -> Result<Option<QuarantinedEvent>> {
+        let _timer = obs::store_timer("get_quarantined");
+        let row = sqlx::query_as::<_, QuarantinedRow>(&format!(
+            "SELECT {QUARANTINED_COLUMNS} FROM quarantined_events WHERE id = $1"
+        ))
+        .bind(id)
+        .fetch_optional(&mut *self.retry.acquire(&self.pool).await?)
+        .await
+        .map_err(InfraError::Database)?;
+
+        row.map(QuarantinedEvent::try_from)
+            .transpose()
+            .map_err(Into::into)
+    }
+
+    #[instrument(skip(self, error))]
+    async fn record_quarantine_attempt(&self, id: i64, error: &str) -> Result<()> {
+        let _timer = obs::store_timer("record_quarantine_attempt");
+        sqlx::query(
+            "UPDATE quarantined_events \
+             SET error = $2, attempts = attempts + 1, last_attempt_at = NOW() WHERE id = $1",
+        )
+        .bind(id)
+        .bind(error)
+        .execute(&mut *self.retry.acquire(&self.pool).await?)
+        .await
+        .map_err(InfraError::Database)?;
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn release_quarantined(&self, id: i64) -> Result<bool> {
+        let _timer = obs::store_timer("release_quarantined");
+        let result = sqlx::query("DELETE FROM quarantined_events WHERE id = $1")
+            .bind(id)
+            .execute(&mut *self.retry.acquire(&self.pool).await?)
+            .await
+            .map_err(InfraError::Database)?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    #[instrument(skip(self))]
+    async fn quarantine_depth(&self) -> Result<u64> {
+        let _timer = obs::store_timer("quarantine_depth");
+        let depth: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM quarantined_events")
+            .fetch_one(&mut *self.retry.acquire(&self.pool).await?)
+            .await
+            .map_err(InfraError::Database)?;
+        Ok(depth as u64)
     }
 }
 
@@ -0,0 +1,206 @@
+//! Retries of store operations that failed on a lost connection.
+//!
+//! Only errors that say nothing about the statement itself are retried:
+//! I/O errors, pool timeouts, and the SQLSTATE classes of lost connections
+//! (`08`), serialization failures (`40001`, `40P01`), exhausted server
+//! resources (`53`) and server shutdowns (`57P01`..`57P03`). Constraint
+//! violations (class `23`) and every other error reach the caller on the
+//! first attempt.
+//!
+//! The [`PostgresStore`](super::PostgresStore) retries acquiring connections
+//! and beginning transactions, which is where a restarted server shows up
+//! first: the pool pings idle connections before handing them out. A
+//! statement interrupted mid-flight is not retried here, as it may have been
+//! part of a batch whose transaction is gone; see [`is_unavailable`] for how
+//! the pipeline recovers from those.
+
+use std::future::Future;
+use std::time::Duration;
+
+use sqlx::Transaction;
+use sqlx::pool::PoolConnection;
+use sqlx::postgres::{PgPool, Postgres};
+use tracing::warn;
+
+use crate::error::{AppError, InfraError, Result};
+use crate::obs;
+
+/// Upper bound of the delay between attempts.
+const MAX_DELAY: Duration = Duration::from_secs(5);
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// CLASSIFICATION
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Check if a database error may succeed when tried again.
+#[must_use]
+pub fn is_retryable(error: &sqlx::Error) -> bool {
+    match error {
+        sqlx::Error::Io(_) | sqlx::Error::PoolTimedOut => true,
+        sqlx::Error::Database(db) => db.code().is_some_and(|code| retryable_sqlstate(&code)),
+        _ => false,
+    }
+}
+
+/// Check if `code` is a SQLSTATE worth retrying.
+fn retryable_sqlstate(code: &str) -> bool {
+    matches!(code, "40001" | "40P01" | "57P01" | "57P02" | "57P03")
+        || code.starts_with("08")
+        || code.starts_with("53")
+}
+
+/// Check if `error` means the store could not be reached, rather than that
+/// the operation was wrong.
+///
+/// The pipeline pauses on these until the store answers again, instead of
+/// skipping the log; see [`Pipeline`](crate::indexer::Pipeline).
+#[must_use]
+pub fn is_unavailable(error: &AppError) -> bool {
+    match error {
+        AppError::Infra(InfraError::Database(e)) => is_retryable(e),
+        AppError::Infra(InfraError::PoolExhausted) => true,
+        _ => false,
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// RETRY POLICY
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Delay before the attempt after one that waited `delay`.
+#[must_use]
+pub fn backoff(delay: Duration) -> Duration {
+    (delay * 2).min(MAX_DELAY)
+}
+
+/// How often and how patiently a store operation is retried.
+///
+/// The delay doubles after each attempt, up to 5 seconds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Attempts after the first; 0 never retries.
+    pub max_retries: u32,
+    /// Delay before the first retry.
+    pub delay: Duration,
+}
+
+impl RetryPolicy {
+    /// Three retries, the first after 100ms.
+    pub const DEFAULT: Self = Self::new(3, Duration::from_millis(100));
+
+    /// No retries.
+    pub const NONE: Self = Self::new(0, Duration::ZERO);
+
+    /// Retry up to `max_retries` times, the first time after `delay`.
+    #[must_use]
+    pub const fn new(max_retries: u32, delay: Duration) -> Self {
+        Self { max_retries, delay }
+    }
+
+    /// Run `attempt` until it succeeds, fails with an error that is not
+    /// [retryable](is_retryable), or runs out of retries.
+    ///
+    /// Each retry is counted under `operation`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the last attempt.
+    pub async fn run<T, F, Fut>(&self, operation: &'static str, mut attempt: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = std::result::Result<T, sqlx::Error>>,
+    {
+        let mut delay = self.delay;
+        let mut retries = 0;
+        loop {
+            match attempt().await {
+                Ok(value) => return Ok(value),
+                Err(e) if retries < self.max_retries && is_retryable(&e) => {
+                    retries += 1;
+                    obs::record_store_retry(operation);
+                    warn!(operation, attempt = retries, error = %e, "Store unavailable, retrying");
+                    tokio::time::sleep(delay).await;
+                    delay = backoff(delay);
+                }
+                Err(e) => return Err(InfraError::Database(e).into()),
+            }
+        }
+    }
+
+    /// Acquire a pooled connection.
+    pub(super) async fn acquire(&self, pool: &PgPool) -> Result<PoolConnection<Postgres>> {
+        self.run("acquire", || pool.acquire()).await
+    }
+
+    /// Begin a transaction on a pooled connection.
+    pub(super) async fn begin(&self, pool: &PgPool) -> Result<Transaction<'static, Postgres>> {
+        self.run("begin", || pool.begin()).await
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use super::*;
+
+    #[test]
+    fn only_connection_sqlstates_are_retryable() {
+        for code in ["08006", "08003", "40001", "40P01", "53300", "57P01", "57P03"] {
+            assert!(retryable_sqlstate(code), "{code}");
+        }
+        // Unique, foreign key and check violations, syntax errors, timeouts
+        for code in ["23505", "23503", "23514", "42601", "57014"] {
+            assert!(!retryable_sqlstate(code), "{code}");
+        }
+
+        assert!(is_retryable(&sqlx::Error::PoolTimedOut));
+        assert!(!is_retryable(&sqlx::Error::RowNotFound));
+        assert!(is_unavailable(&InfraError::Database(sqlx::Error::PoolTimedOut).into()));
+        assert!(!is_unavailable(&InfraError::Internal("bad row".into()).into()));
+    }
+
+    #[tokio::test]
+    async fn retries_until_success_or_exhaustion() {
+        let policy = RetryPolicy::new(2, Duration::from_millis(1));
+
+        let attempts = AtomicU32::new(0);
+        let value = policy
+            .run("test", || async {
+                if attempts.fetch_add(1, Ordering::SeqCst) < 2 {
+                    Err(sqlx::Error::PoolTimedOut)
+                } else {
+                    Ok(7)
+                }
+            })
+            .await
+            .unwrap();
+        assert_eq!((value, attempts.load(Ordering::SeqCst)), (7, 3));
+
+        let attempts = AtomicU32::new(0);
+        let result: Result<()> = policy
+            .run("test", || async {
+                attempts.fetch_add(1, Ordering::SeqCst);
+                Err(sqlx::Error::PoolTimedOut)
+            })
+            .await;
+        assert!(result.is_err());
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+
+        // Errors about the statement itself are not retried
+        let attempts = AtomicU32::new(0);
+        let result: Result<()> = policy
+            .run("test", || async {
+                attempts.fetch_add(1, Ordering::SeqCst);
+                Err(sqlx::Error::RowNotFound)
+            })
+            .await;
+        assert!(result.is_err());
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,886 @@
+//! Sequenced event log, replayed to reconnecting WebSocket clients.
+//!
+//! Every message published through a [`SequencedPublisher`] gets the next
+//! sequence of its topic from the [`EventLogStore`] before it is sent to the
+//! streaming system, where it travels in the
+//! [`SEQUENCE_HEADER`](super::SEQUENCE_HEADER) header. Once the streaming
+//! system accepted the messages they are marked published and handed to the
+//! live [`Subscription`]s, so Iggy consumers and WebSocket clients see the
+//! same sequences.
+//!
+//! ```text
+//! OutboxRelay / RoundWatcher
+//!        │
+//!        ▼
+//! SequencedPublisher ──▶ log_events (assigns sequences)
+//!        ├──▶ inner.publish_acknowledged (Iggy, `sequence` header)
+//!        └──▶ mark_events_published ──▶ live Subscriptions
+//! ```
+//!
+//! # Replay
+//!
+//! A client resuming from a sequence is replayed the published events from
+//! there on, followed by a [`SubscriptionItem::ReplayComplete`] marker, and
+//! then the live events. Events published while the replay is read are
+//! delivered once. A client whose sequence is no longer kept gets
+//! [`SubscriptionItem::ReplayUnavailable`] with the current sequence instead,
+//! and the live events after it.
+//!
+//! Published events are kept for `replay_retention_secs`, up to
+//! `replay_max_events` per topic.
+//!
+//! # Envelopes
+//!
+//! Payloads that are wire [`Envelope`](super::Envelope)s get their sequence
+//! stamped into the envelope too, both when sequenced and when replayed, as
+//! the store logs them before the sequence is known. Other payloads are
+//! logged and delivered as they are.
+//!
+//! # Ordering
+//!
+//! A message keeps its sequence when it is published again, so a message
+//! retried after a failed publish is delivered late, after messages with
+//! higher sequences. Sequences are otherwise delivered in order.
+
+use std::collections::{HashSet, VecDeque};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, warn};
+
+use super::wire;
+use crate::config::WebSocketSettings;
+use crate::error::Result;
+use crate::ports::{EventLogStore, EventPublisher, IdentifiedMessage};
+use crate::types::entities::LoggedEvent;
+use crate::types::events::GhostnetEvent;
+
+/// Events buffered for live subscriptions; slower subscriptions catch up
+/// from the store.
+const LIVE_CAPACITY: usize = 4096;
+
+/// Most events read from the store at once while replaying.
+const REPLAY_PAGE_SIZE: u32 = 500;
+
+/// Interval between deletions of events past the retention.
+const PURGE_INTERVAL: Duration = Duration::from_secs(60);
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// EVENT LOG
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Sequenced log of the published events, with live delivery.
+pub struct EventLog {
+    /// Store the events are logged in.
+    store: Arc<dyn EventLogStore>,
+    /// Published events, to live subscriptions.
+    live: broadcast::Sender<Arc<LoggedEvent>>,
+    /// How long published events are kept.
+    retention: Duration,
+    /// Most published events kept per topic.
+    max_events: u64,
+}
+
+impl std::fmt::Debug for EventLog {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventLog")
+            .field("subscriptions", &self.live.receiver_count())
+            .field("retention", &self.retention)
+            .field("max_events", &self.max_events)
+            .finish_non_exhaustive()
+    }
+}
+
+impl EventLog {
+    /// Create a new event log.
+    #[must_use]
+    pub fn new(store: Arc<dyn EventLogStore>, settings: &WebSocketSettings) -> Self {
+        let (live, _) = broadcast::channel(LIVE_CAPACITY);
+        Self {
+            store,
+            live,
+            retention: settings.replay_retention(),
+            max_events: settings.replay_max_events.max(1),
+        }
+    }
+
+    /// Assign sequences to `messages` of `topic`, returning them with their
+    /// sequence set, in the envelope too.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the messages cannot be logged.
+    pub async fn sequence(
+        &self,
+        topic: &str,
+        messages: &[IdentifiedMessage],
+    ) -> Result<Vec<IdentifiedMessage>> {
+        let sequences = self.store.log_events(topic, messages).await?;
+        Ok(messages
+            .iter()
+            .zip(sequences)
+            .map(|(message, sequence)| IdentifiedMessage {
+                id: message.id,
+                payload: wire::with_sequence(&message.payload, sequence)
+                    .map_or_else(|| message.payload.clone(), Bytes::from),
+                sequence: Some(sequence),
+            })
+            .collect())
+    }
+
+    /// Mark sequenced `messages` of `topic` as published at `at` and deliver
+    /// them to the live subscriptions.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the messages cannot be marked.
+    pub async fn published(
+        &self,
+        topic: &str,
+        messages: &[IdentifiedMessage],
+        at: DateTime<Utc>,
+    ) -> Result<()> {
+        let sequences: Vec<u64> = messages.iter().filter_map(|m| m.sequence).collect();
+        self.store
+            .mark_events_published(topic, &sequences, at)
+            .await?;
+        for message in messages {
+            let Some(sequence) = message.sequence else {
+                continue;
+            };
+            // No receivers just means nobody is subscribed
+            let _ = self.live.send(Arc::new(LoggedEvent {
+                topic: topic.to_string(),
+                sequence,
+                payload: message.payload.to_vec(),
+                published_at: at,
+            }));
+        }
+        Ok(())
+    }
+
+    /// Subscribe to the events of `topic`, replaying the published events
+    /// from `from_sequence` on first.
+    ///
+    /// Without `from_sequence` nothing is replayed; the subscription starts
+    /// with [`SubscriptionItem::ReplayComplete`] at the current sequence.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the log cannot be read.
+    pub async fn subscribe(
+        self: &Arc<Self>,
+        topic: &str,
+        from_sequence: Option<u64>,
+    ) -> Result<Subscription> {
+        // Receive live events before reading the store, so none falls between
+        let mut subscription = Subscription {
+            log: Arc::clone(self),
+            topic: topic.to_string(),
+            live: self.live.subscribe(),
+            pending: VecDeque::new(),
+            replayed: HashSet::new(),
+            last_sequence: 0,
+        };
+        if let Some(from) = from_sequence {
+            subscription.replay(from).await?;
+        } else {
+            let bounds = self.store.event_log_bounds(topic).await?;
+            subscription.last_sequence = bounds.latest;
+            subscription
+                .pending
+                .push_back(SubscriptionItem::ReplayComplete {
+                    sequence: bounds.latest,
+                });
+        }
+        Ok(subscription)
+    }
+
+    /// Delete the events past the retention at `now`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the log cannot be updated.
+    pub async fn purge(&self, now: DateTime<Utc>) -> Result<u64> {
+        let retention = chrono::Duration::from_std(self.retention).unwrap_or(chrono::Duration::MAX);
+        let purged = self
+            .store
+            .purge_event_log(now - retention, self.max_events)
+            .await?;
+        if purged > 0 {
+            debug!(purged, "Purged event log");
+        }
+        Ok(purged)
+    }
+
+    /// Spawn the task purging events past the retention every minute, until
+    /// `shutdown` is cancelled.
+    pub fn spawn_purge_task(self: &Arc<Self>, shutdown: CancellationToken) -> JoinHandle<()> {
+        let log = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(PURGE_INTERVAL);
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+            loop {
+                tokio::select! {
+                    () = shutdown.cancelled() => break,
+                    _ = ticker.tick() => {
+                        if let Err(e) = log.purge(Utc::now()).await {
+                            warn!(error = %e, "Purging event log failed");
+                        }
+                    }
+                }
+            }
+            info!("Event log purge stopped");
+        })
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// SUBSCRIPTION
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// What a [`Subscription`] delivers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubscriptionItem {
+    /// A published event, replayed or live.
+    Event(Arc<LoggedEvent>),
+    /// The replay is complete: the events up to `sequence` were delivered,
+    /// and the live events follow.
+    ReplayComplete {
+        /// Last sequence replayed, or the current one if none was.
+        sequence: u64,
+    },
+    /// The requested sequence is no longer kept; the live events after
+    /// `current_sequence` follow.
+    ReplayUnavailable {
+        /// Latest published sequence.
+        current_sequence: u64,
+    },
+}
+
+/// Events of one topic, replayed and then live.
+///
+/// Created by [`EventLog::subscribe`].
+#[derive(Debug)]
+pub struct Subscription {
+    /// Log the events come from.
+    log: Arc<EventLog>,
+    /// Topic subscribed to.
+    topic: String,
+    /// Live events of all topics.
+    live: broadcast::Receiver<Arc<LoggedEvent>>,
+    /// Items to deliver before the next live event.
+    pending: VecDeque<SubscriptionItem>,
+    /// Replayed sequences that may still arrive live.
+    replayed: HashSet<u64>,
+    /// Highest sequence delivered.
+    last_sequence: u64,
+}
+
+impl Subscription {
+    /// Topic subscribed to.
+    #[must_use]
+    pub fn topic(&self) -> &str {
+        &self.topic
+    }
+
+    /// Wait for the next item, or `None` once the log is gone.
+    ///
+    /// A subscription falling too far behind the live events catches up
+    /// from the store.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if catching up fails.
+    pub async fn next(&mut self) -> Result<Option<SubscriptionItem>> {
+        loop {
+            if let Some(item) = self.pending.pop_front() {
+                return Ok(Some(item));
+            }
+            let event = match self.live.recv().await {
+                Ok(event) => event,
+                Err(RecvError::Lagged(missed)) => {
+                    warn!(topic = %self.topic, missed, "Subscription lagged, catching up");
+                    self.live = self.live.resubscribe();
+                    self.catch_up(self.last_sequence + 1).await?;
+                    continue;
+                }
+                Err(RecvError::Closed) => return Ok(None),
+            };
+            if event.topic != self.topic || self.replayed.remove(&event.sequence) {
+                continue;
+            }
+            // Publishing per topic is sequential: every replayed event that
+            // is still to arrive live came before this one
+            self.replayed.clear();
+            self.last_sequence = self.last_sequence.max(event.sequence);
+            return Ok(Some(SubscriptionItem::Event(event)));
+        }
+    }
+
+    /// Queue the published events from `from` on, then the seam marker.
+    async fn replay(&mut self, from: u64) -> Result<()> {
+        if self.catch_up(from).await? {
+            self.pending.push_back(SubscriptionItem::ReplayComplete {
+                sequence: self.last_sequence,
+            });
+        }
+        Ok(())
+    }
+
+    /// Queue the published events from `from` on, returning whether they
+    /// were all still kept.
+    ///
+    /// Queues [`SubscriptionItem::ReplayUnavailable`] instead if they were
+    /// not.
+    async fn catch_up(&mut self, from: u64) -> Result<bool> {
+        let store = &self.log.store;
+        let bounds = store.event_log_bounds(&self.topic).await?;
+        if !bounds.retains(from) {
+            self.last_sequence = bounds.latest;
+            self.pending.push_back(SubscriptionItem::ReplayUnavailable {
+                current_sequence: bounds.latest,
+            });
+            return Ok(false);
+        }
+
+        self.last_sequence = self
+            .last_sequence
+            .max(bounds.latest.min(from.saturating_sub(1)));
+        let mut next = from;
+        loop {
+            let page = store
+                .get_logged_events(&self.topic, next, REPLAY_PAGE_SIZE)
+                .await?;
+            let full = page.len() >= REPLAY_PAGE_SIZE as usize;
+            for mut event in page {
+                next = event.sequence + 1;
+                if let Some(payload) = wire::with_sequence(&event.payload, event.sequence) {
+                    event.payload = payload;
+                }
+                self.last_sequence = self.last_sequence.max(event.sequence);
+                self.replayed.insert(event.sequence);
+                self.pending
+                    .push_back(SubscriptionItem::Event(Arc::new(event)));
+            }
+            if !full {
+                return Ok(true);
+            }
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// SEQUENCED PUBLISHER
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Publisher assigning sequences to acknowledged messages through an
+/// [`EventLog`].
+///
+/// Buffered publishes go to the inner publisher as they are, without a
+/// sequence.
+#[derive(Debug)]
+pub struct SequencedPublisher<P> {
+    /// Publisher the messages are sent through.
+    inner: Arc<P>,
+    /// Log assigning the sequences.
+    log: Arc<EventLog>,
+}
+
+impl<P> SequencedPublisher<P> {
+    /// Create a publisher sequencing the messages of `inner` in `log`.
+    #[must_use]
+    pub const fn new(inner: Arc<P>, log: Arc<EventLog>) -> Self {
+        Self { inner, log }
+    }
+}
+
+#[async_trait]
+impl<P: EventPublisher> EventPublisher for SequencedPublisher<P> {
+    async fn publish(&self, event: &GhostnetEvent) -> Result<()> {
+        self.inner.publish(event).await
+    }
+
+    async fn publish_to_topic(&self, topic: &str, payload: &[u8]) -> Result<()> {
+        self.inner.publish_to_topic(topic, payload).await
+    }
+
+    async fn publish_batch(&self, events: &[GhostnetEvent]) -> Result<()> {
+        self.inner.publish_batch(events).await
+    }
+
+    /// Log the messages, publish them with their sequences and mark them
+    /// published.
+    ///
+    /// A message published again keeps its sequence.
+    async fn publish_acknowledged(
+        &self,
+        topic: &str,
+        messages: &[IdentifiedMessage],
+    ) -> Result<()> {
+        if messages.is_empty() {
+            return Ok(());
+        }
+        let sequenced = self.log.sequence(topic, messages).await?;
+        self.inner.publish_acknowledged(topic, &sequenced).await?;
+        self.log.published(topic, &sequenced, Utc::now()).await
+    }
+
+    async fn flush(&self) -> Result<()> {
+        self.inner.flush().await
+    }
+
+    fn is_connected(&self) -> bool {
+        self.inner.is_connected()
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::significant_drop_tightening)]
+pub mod tests {
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use parking_lot::Mutex;
+
+    use super::*;
+    use crate::error::InfraError;
+    use crate::types::entities::EventLogBounds;
+
+    /// Logged message.
+    #[derive(Debug, Clone)]
+    struct Row {
+        id: u128,
+        event: LoggedEvent,
+        published: bool,
+    }
+
+    /// In-memory event log following the store's contract.
+    #[derive(Debug, Default)]
+    pub struct MemoryEventLog {
+        /// Rows per topic, in sequence order.
+        topics: Mutex<HashMap<String, Vec<Row>>>,
+        /// Last sequence per topic.
+        sequences: Mutex<HashMap<String, u64>>,
+    }
+
+    #[async_trait]
+    impl EventLogStore for MemoryEventLog {
+        async fn log_events(
+            &self,
+            topic: &str,
+            messages: &[IdentifiedMessage],
+        ) -> Result<Vec<u64>> {
+            let mut topics = self.topics.lock();
+            let rows = topics.entry(topic.to_string()).or_default();
+            let mut sequences = self.sequences.lock();
+            let last = sequences.entry(topic.to_string()).or_default();
+            let mut assigned = Vec::new();
+            for message in messages {
+                if let Some(row) = rows.iter().find(|row| row.id == message.id) {
+                    assigned.push(row.event.sequence);
+                    continue;
+                }
+                *last += 1;
+                rows.push(Row {
+                    id: message.id,
+                    event: LoggedEvent {
+                        topic: topic.to_string(),
+                        sequence: *last,
+                        payload: message.payload.to_vec(),
+                        published_at: Utc::now(),
+                    },
+                    published: false,
+                });
+                assigned.push(*last);
+            }
+            Ok(assigned)
+        }
+
+        async fn mark_events_published(
+            &self,
+            topic: &str,
+            sequences: &[u64],
+            at: DateTime<Utc>,
+        ) -> Result<()> {
+            if let Some(rows) = self.topics.lock().get_mut(topic) {
+                for row in rows.iter_mut() {
+                    if sequences.contains(&row.event.sequence) {
+                        row.published = true;
+                        row.event.published_at = at;
+                    }
+                }
+            }
+            Ok(())
+        }
+
+        async fn get_logged_events(
+            &self,
+            topic: &str,
+            from_sequence: u64,
+            limit: u32,
+        ) -> Result<Vec<LoggedEvent>> {
+            let mut events: Vec<LoggedEvent> = self
+                .topics
+                .lock()
+                .get(topic)
+                .into_iter()
+                .flatten()
+                .filter(|row| row.published && row.event.sequence >= from_sequence)
+                .map(|row| row.event.clone())
+                .collect();
+            events.sort_by_key(|event| event.sequence);
+            events.truncate(limit as usize);
+            Ok(events)
+        }
+
+        async fn event_log_bounds(&self, topic: &str) -> Result<EventLogBounds> {
+            let topics = self.topics.lock();
+            let rows = topics.get(topic).map(Vec::as_slice).unwrap_or_default();
+            Ok(EventLogBounds {
+                oldest: rows.iter().map(|row| row.event.sequence).min(),
+                latest: rows
+                    .iter()
+                    .filter(|row| row.published)
+                    .map(|row| row.event.sequence)
+                    .max()
+                    .unwrap_or_default(),
+            })
+        }
+
+        async fn purge_event_log(&self, before: DateTime<Utc>, keep: u64) -> Result<u64> {
+            let mut purged = 0;
+            for rows in self.topics.lock().values_mut() {
+                let published = rows.iter().filter(|row| row.published);
+                let Some(latest) = published.clone().map(|row| row.event.sequence).max() else {
+                    continue;
+                };
+                let expired = published
+                    .filter(|row| row.event.published_at < before)
+                    .map(|row| row.event.sequence)
+                    .max()
+                    .unwrap_or_default();
+                let cut = expired.max(latest.saturating_sub(keep)).min(latest - 1);
+                let count = rows.len();
+                rows.retain(|row| !row.published || row.event.sequence > cut);
+                purged += (count - rows.len()) as u64;
+            }
+            Ok(purged)
+        }
+    }
+
+    /// Publisher recording the sequences it was given per topic.
+    #[derive(Debug, Default)]
+    struct RecordingPublisher {
+        /// Delivered (topic, ID, sequence), in delivery order.
+        delivered: Mutex<Vec<(String, u128, Option<u64>)>>,
+        /// Number of upcoming acknowledged publishes that fail.
+        failures: AtomicU32,
+    }
+
+    #[async_trait]
+    impl EventPublisher for RecordingPublisher {
+        async fn publish(&self, _event: &GhostnetEvent) -> Result<()> {
+            Ok(())
+        }
+
+        async fn publish_to_topic(&self, _topic: &str, _payload: &[u8]) -> Result<()> {
+            Ok(())
+        }
+
+        async fn publish_batch(&self, _events: &[GhostnetEvent]) -> Result<()> {
+            Ok(())
+        }
+
+        async fn publish_acknowledged(
+            &self,
+            topic: &str,
+            messages: &[IdentifiedMessage],
+        ) -> Result<()> {
+            let failed = self
+                .failures
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                .is_ok();
+            if failed {
+                return Err(InfraError::Streaming("injected failure".into()).into());
+            }
+            let mut delivered = self.delivered.lock();
+            for message in messages {
+                delivered.push((topic.to_string(), message.id, message.sequence));
+            }
+            Ok(())
+        }
+
+        async fn flush(&self) -> Result<()> {
+            Ok(())
+        }
+
+        fn is_connected(&self) -> bool {
+            true
+        }
+    }
+
+    fn settings() -> WebSocketSettings {
+        WebSocketSettings {
+            max_connections: 10,
+            ping_interval_ms: 30_000,
+            pong_timeout_ms: 10_000,
+            replay_retention_secs: 3600,
+            replay_max_events: 10_000,
+        }
+    }
+
+    fn message(id: u128) -> IdentifiedMessage {
+        IdentifiedMessage {
+            id,
+            payload: Bytes::from(format!("{{\"id\":{id}}}")),
+            sequence: None,
+        }
+    }
+
+    /// Publish messages `ids` to `topic`, one call each.
+    async fn publish<P: EventPublisher>(publisher: &P, topic: &str, ids: std::ops::Range<u128>) {
+        for id in ids {
+            publisher
+                .publish_acknowledged(topic, &[message(id)])
+                .await
+                .unwrap();
+        }
+    }
+
+    /// Take the items already delivered to `subscription`.
+    async fn drain(subscription: &mut Subscription) -> Vec<SubscriptionItem> {
+        let mut items = Vec::new();
+        while let Ok(Ok(Some(item))) =
+            tokio::time::timeout(Duration::from_millis(20), subscription.next()).await
+        {
+            items.push(item);
+        }
+        items
+    }
+
+    fn sequences(items: &[SubscriptionItem]) -> Vec<u64> {
+        items
+            .iter()
+            .filter_map(|item| match item {
+                SubscriptionItem::Event(event) => Some(event.sequence),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn setup() -> (
+        Arc<EventLog>,
+        SequencedPublisher<RecordingPublisher>,
+        Arc<RecordingPublisher>,
+    ) {
+        let log = Arc::new(EventLog::new(
+            Arc::new(MemoryEventLog::default()),
+            &settings(),
+        ));
+        let inner = Arc::new(RecordingPublisher::default());
+        let publisher = SequencedPublisher::new(Arc::clone(&inner), Arc::clone(&log));
+        (log, publisher, inner)
+    }
+
+    #[tokio::test]
+    async fn sequences_are_shared_with_the_streaming_system() {
+        let (log, publisher, inner) = setup();
+        let mut subscription = log.subscribe("positions", None).await.unwrap();
+
+        publish(&publisher, "positions", 10..13).await;
+        publish(&publisher, "market", 20..21).await;
+
+        let delivered = inner.delivered.lock().clone();
+        assert_eq!(
+            delivered
+                .iter()
+                .map(|(topic, _, sequence)| (topic.as_str(), sequence.unwrap()))
+                .collect::<Vec<_>>(),
+            [
+                ("positions", 1),
+                ("positions", 2),
+                ("positions", 3),
+                ("market", 1)
+            ]
+        );
+        let items = drain(&mut subscription).await;
+        assert_eq!(items[0], SubscriptionItem::ReplayComplete { sequence: 0 });
+        assert_eq!(sequences(&items), [1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn republished_messages_keep_their_sequence() {
+        let (log, publisher, inner) = setup();
+        inner.failures.store(1, Ordering::SeqCst);
+
+        assert!(
+            publisher
+                .publish_acknowledged("positions", &[message(1)])
+                .await
+                .is_err()
+        );
+        publish(&publisher, "positions", 2..3).await;
+        publish(&publisher, "positions", 1..2).await;
+
+        let delivered: Vec<_> = inner
+            .delivered
+            .lock()
+            .iter()
+            .map(|(_, id, sequence)| (*id, sequence.unwrap()))
+            .collect();
+        assert_eq!(delivered, [(2, 2), (1, 1)]);
+
+        let mut subscription = log.subscribe("positions", Some(1)).await.unwrap();
+        assert_eq!(sequences(&drain(&mut subscription).await), [1, 2]);
+    }
+
+    #[tokio::test]
+    async fn envelopes_carry_their_sequence_live_and_replayed() {
+        let (log, publisher, _) = setup();
+        let mut live = log.subscribe("positions", None).await.unwrap();
+        let envelope = serde_json::json!({
+            "schema_version": 1,
+            "topic": "positions",
+            "sequence": null,
+            "timestamp": "2026-01-01T00:00:00Z",
+            "event": {},
+        });
+        let message = IdentifiedMessage {
+            id: 1,
+            payload: serde_json::to_vec(&envelope).unwrap().into(),
+            sequence: None,
+        };
+        publisher
+            .publish_acknowledged("positions", &[message])
+            .await
+            .unwrap();
+
+        let mut replayed = log.subscribe("positions", Some(1)).await.unwrap();
+        for items in [drain(&mut live).await, drain(&mut replayed).await] {
+            let event = items
+                .iter()
+                .find_map(|item| match item {
+                    SubscriptionItem::Event(event) => Some(event),
+                    _ => None,
+                })
+                .unwrap();
+            let payload: serde_json::Value = serde_json::from_slice(&event.payload).unwrap();
+            assert_eq!(payload["sequence"], 1);
+        }
+    }
+
+    #[tokio::test]
+    async fn reconnecting_clients_get_exactly_the_missed_events_before_live_ones() {
+        let (log, publisher, _) = setup();
+
+        let mut first = log.subscribe("positions", None).await.unwrap();
+        publish(&publisher, "positions", 0..5).await;
+        let seen = sequences(&drain(&mut first).await);
+        assert_eq!(seen, [1, 2, 3, 4, 5]);
+        drop(first);
+
+        // Missed while disconnected
+        publish(&publisher, "positions", 5..8).await;
+
+        let resume = seen.last().unwrap() + 1;
+        let mut second = log.subscribe("positions", Some(resume)).await.unwrap();
+        publish(&publisher, "positions", 8..10).await;
+
+        let items = drain(&mut second).await;
+        let seam = items
+            .iter()
+            .position(|item| matches!(item, SubscriptionItem::ReplayComplete { .. }))
+            .unwrap();
+        assert_eq!(
+            items[seam],
+            SubscriptionItem::ReplayComplete { sequence: 8 }
+        );
+        assert_eq!(sequences(&items[..seam]), [6, 7, 8]);
+        assert_eq!(sequences(&items[seam + 1..]), [9, 10]);
+    }
+
+    #[tokio::test]
+    async fn events_published_during_the_replay_are_delivered_once() {
+        let (log, publisher, _) = setup();
+        publish(&publisher, "positions", 0..3).await;
+
+        // Subscribed before the store is read: the last events are both
+        // replayed and received live
+        let mut subscription = log.subscribe("positions", Some(2)).await.unwrap();
+        log.published(
+            "positions",
+            &[IdentifiedMessage {
+                sequence: Some(3),
+                ..message(2)
+            }],
+            Utc::now(),
+        )
+        .await
+        .unwrap();
+        publish(&publisher, "positions", 3..4).await;
+
+        let items = drain(&mut subscription).await;
+        assert_eq!(sequences(&items), [2, 3, 4]);
+        assert_eq!(items[2], SubscriptionItem::ReplayComplete { sequence: 3 });
+    }
+
+    #[tokio::test]
+    async fn clients_beyond_the_retention_get_the_current_sequence() {
+        let (log, publisher, _) = setup();
+        publish(&publisher, "positions", 0..5).await;
+        let before = Utc::now() - chrono::Duration::hours(1);
+        log.store.purge_event_log(before, 2).await.unwrap();
+
+        let mut subscription = log.subscribe("positions", Some(2)).await.unwrap();
+        publish(&publisher, "positions", 5..6).await;
+        let items = drain(&mut subscription).await;
+        assert_eq!(
+            items[0],
+            SubscriptionItem::ReplayUnavailable {
+                current_sequence: 5
+            }
+        );
+        assert_eq!(sequences(&items), [6]);
+
+        // Still kept
+        let mut subscription = log.subscribe("positions", Some(4)).await.unwrap();
+        assert_eq!(sequences(&drain(&mut subscription).await), [4, 5, 6]);
+    }
+
+    #[tokio::test]
+    async fn purge_keeps_the_latest_event_of_each_topic() {
+        let (log, publisher, _) = setup();
+        publish(&publisher, "positions", 0..3).await;
+
+        let purged = log
+            .purge(Utc::now() + chrono::Duration::hours(2))
+            .await
+            .unwrap();
+        assert_eq!(purged, 2);
+        let bounds = log.store.event_log_bounds("positions").await.unwrap();
+        assert_eq!(
+            bounds,
+            EventLogBounds {
+                oldest: Some(3),
+                latest: 3
+            }
+        );
+        assert!(bounds.retains(3));
+        assert!(bounds.retains(4));
+        assert!(!bounds.retains(2));
+    }
+}
@@ -1,44 +1,129 @@
 //! Apache Iggy event publisher implementation.
 //!
 //! Implements the `EventPublisher` port using Apache Iggy as the streaming backend.
+//!
+//! # Delivery Semantics
+//!
+//! Published messages are buffered and sent in batches once `max_batch_size`
+//! messages have accumulated, or when the background flush task fires every
+//! `max_batch_delay` (see [`IggyPublisher::spawn_flush_task`]).
+//!
+//! A failed batch is retried with exponential backoff. After `max_retries`
+//! retries the batch is routed to a [`DeadLetterSink`] instead of being dropped.
+//! If the sink fails too, as a dead-letter topic on the same server does in
+//! an outage, the batch goes back to the front of the buffer for the next
+//! flush.
+//! Delivery is at-least-once: a send that times out after reaching the server
+//! may be retried and delivered twice.
+//!
+//! [`EventPublisher::publish_acknowledged`] bypasses the buffer: the messages
+//! are sent right away, with their IDs, and a failure is returned to the
+//! caller instead of being retried or dead-lettered. With message
+//! deduplication enabled on the Iggy server, redeliveries of an ID are
+//! dropped. A message's sequence, if it has one, is sent in the
+//! [`SEQUENCE_HEADER`] header.
 
+use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::Arc;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::Duration;
 
 use async_trait::async_trait;
 use bytes::Bytes;
+use chrono::{DateTime, Utc};
 use iggy::client::{Client, MessageClient, StreamClient, TopicClient};
 use iggy::clients::client::IggyClient;
 use iggy::compression::compression_algorithm::CompressionAlgorithm;
 use iggy::identifier::Identifier;
 use iggy::messages::send_messages::{Message, Partitioning};
+use iggy::models::header::{HeaderKey, HeaderValue};
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::topic_size::MaxTopicSize;
+use parking_lot::Mutex;
+use serde::Serialize;
+use tokio::io::AsyncWriteExt;
 use tokio::sync::RwLock;
-use tracing::{debug, info, instrument};
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, error, info, instrument, warn};
 
 use crate::config::IggySettings;
 use crate::error::{InfraError, Result};
-use crate::ports::EventPublisher;
+use crate::obs;
+use crate::ports::{EventPublisher, IdentifiedMessage};
 use crate::types::events::GhostnetEvent;
 
 use super::topics::Topic;
+use super::wire::WireSchemas;
 
-/// Apache Iggy-based event publisher.
+/// Upper bound for a single retry backoff.
+const MAX_RETRY_BACKOFF: Duration = Duration::from_secs(10);
+
+/// Message header carrying the sequence of an [`IdentifiedMessage`], the
+/// same the WebSocket API delivers.
+pub const SEQUENCE_HEADER: &str = "sequence";
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TRANSPORT
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Low-level message transport used by [`IggyPublisher`].
+///
+/// Separates buffering, retry and dead-letter logic from the wire protocol so
+/// the publisher can be exercised without a running Iggy server.
+#[async_trait]
+pub trait MessageTransport: Send + Sync {
+    /// Send a batch of payloads to a topic in a single round-trip.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the batch could not be delivered.
+    async fn send(&self, topic: &str, payloads: &[Bytes]) -> Result<()>;
+
+    /// Send a batch of messages with their IDs to a topic in a single
+    /// round-trip.
+    ///
+    /// Transports without message IDs send the payloads only.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the batch could not be delivered.
+    async fn send_identified(&self, topic: &str, messages: &[IdentifiedMessage]) -> Result<()> {
+        let payloads: Vec<Bytes> = messages.iter().map(|m| m.payload.clone()).collect();
+        self.send(topic, &payloads).await
+    }
+
+    /// Release the underlying connection.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the connection could not be closed cleanly.
+    async fn close(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Check if the transport is connected.
+    fn is_connected(&self) -> bool;
+}
+
+/// Iggy TCP transport.
 ///
-/// Connects to an Iggy server and publishes GHOSTNET events to appropriate topics.
-/// Handles stream/topic creation lazily and manages reconnection.
+/// Handles stream/topic creation lazily and tracks connection state.
 ///
 /// # Thread Safety
 ///
 /// This type is `Send + Sync` and can be shared across tasks.
-pub struct IggyPublisher {
+pub struct IggyTransport {
     /// The Iggy client.
     client: Arc<IggyClient>,
     /// Stream name for all GHOSTNET events.
     stream_name: String,
     /// Number of partitions per topic.
     partition_count: u32,
+    /// Topics created in addition to [`Topic::all`] (e.g. the dead-letter topic).
+    extra_topics: Vec<String>,
     /// Whether we're connected to the Iggy server.
     connected: AtomicBool,
     /// Whether we've initialized the stream and topics.
@@ -47,9 +132,9 @@ pub struct IggyPublisher {
     init_lock: RwLock<()>,
 }
 
-impl std::fmt::Debug for IggyPublisher {
+impl std::fmt::Debug for IggyTransport {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("IggyPublisher")
+        f.debug_struct("IggyTransport")
             .field("stream_name", &self.stream_name)
             .field("partition_count", &self.partition_count)
             .field("connected", &self.connected.load(Ordering::SeqCst))
@@ -58,11 +143,10 @@ impl std::fmt::Debug for IggyPublisher {
     }
 }
 
-impl IggyPublisher {
-    /// Create a new Iggy publisher from settings.
+impl IggyTransport {
+    /// Create a new Iggy transport from settings.
     ///
-    /// This creates the client but does NOT connect. Call `connect()` to establish
-    /// the connection, or let it connect lazily on first publish.
+    /// This creates the client but does NOT connect.
     ///
     /// # Errors
     ///
@@ -74,10 +158,16 @@ impl IggyPublisher {
             .build()
             .map_err(|e| InfraError::Streaming(format!("Failed to create Iggy client: {e}")))?;
 
+        let extra_topics = match DeadLetterSink::from_settings(settings) {
+            DeadLetterSink::Topic(topic) => vec![topic],
+            DeadLetterSink::File(_) => Vec::new(),
+        };
+
         Ok(Self {
             client: Arc::new(client),
             stream_name: settings.stream_name.clone(),
             partition_count: settings.partition_count,
+            extra_topics,
             connected: AtomicBool::new(false),
             initialized: AtomicBool::new(false),
             init_lock: RwLock::new(()),
@@ -120,7 +210,7 @@ impl IggyPublisher {
 
     /// Ensure the stream and topics exist.
     ///
-    /// This is called lazily on first publish and is idempotent.
+    /// This is called lazily on first send and is idempotent.
     /// Will auto-connect if not already connected.
     #[instrument(skip(self))]
     async fn ensure_initialized(&self) -> Result<()> {
@@ -145,7 +235,10 @@ impl IggyPublisher {
 
         // Create all topics
         for topic in Topic::all() {
-            self.ensure_topic_exists(*topic).await?;
+            self.ensure_topic_exists(topic.as_str()).await?;
+        }
+        for topic in &self.extra_topics {
+            self.ensure_topic_exists(topic).await?;
         }
 
         self.initialized.store(true, Ordering::SeqCst);
@@ -198,10 +291,10 @@ impl IggyPublisher {
     }
 
     /// Ensure a topic exists within the stream.
-    async fn ensure_topic_exists(&self, topic: Topic) -> Result<()> {
+    async fn ensure_topic_exists(&self, topic: &str) -> Result<()> {
         let stream_id = Identifier::from_str_value(&self.stream_name)
             .map_err(|e| InfraError::Streaming(format!("Invalid stream name: {e}")))?;
-        let topic_id = Identifier::from_str_value(topic.as_str())
+        let topic_id = Identifier::from_str_value(topic)
             .map_err(|e| InfraError::Streaming(format!("Invalid topic name: {e}")))?;
 
         // Try to get the topic first
@@ -226,7 +319,7 @@ impl IggyPublisher {
             .client
             .create_topic(
                 &stream_id,
-                topic.as_str(),
+                topic,
                 self.partition_count,
                 CompressionAlgorithm::None, // compression
                 None,                       // replication_factor
@@ -252,38 +345,44 @@ impl IggyPublisher {
         }
     }
 
-    /// Serialize an event to JSON bytes.
-    fn serialize_event(event: &GhostnetEvent) -> Result<Bytes> {
-        serde_json::to_vec(event)
-            .map(Bytes::from)
-            .map_err(|e| InfraError::Streaming(format!("Failed to serialize event: {e}")).into())
-    }
-
-    /// Create an Iggy message from an event.
-    fn create_message(event: &GhostnetEvent) -> Result<Message> {
-        let payload = Self::serialize_event(event)?;
+    /// Create an Iggy message from a payload; ID 0 lets the server assign one.
+    fn create_message(id: u128, payload: &Bytes) -> Message {
         // Message payload length is capped at u32::MAX by Iggy protocol.
         // Practical event payloads are always << 4GB, so this cast is safe.
         #[allow(clippy::cast_possible_truncation)]
         let length = payload.len() as u32;
-        Ok(Message {
-            id: 0, // Server will assign
+        Message {
+            id,
             length,
-            payload,
+            payload: payload.clone(),
             headers: None,
-        })
+        }
     }
 
-    /// Send messages to a topic.
-    #[instrument(skip(self, messages), fields(topic = %topic, count = messages.len()))]
-    async fn send_to_topic(&self, topic: Topic, messages: &mut [Message]) -> Result<()> {
+    /// Create an Iggy message from an identified message, with its sequence
+    /// in the [`SEQUENCE_HEADER`] header.
+    fn create_identified_message(message: &IdentifiedMessage) -> Result<Message> {
+        let mut created = Self::create_message(message.id, &message.payload);
+        if let Some(sequence) = message.sequence {
+            let invalid = |e| InfraError::Streaming(format!("Invalid sequence header: {e}"));
+            let key = HeaderKey::new(SEQUENCE_HEADER).map_err(invalid)?;
+            let value = HeaderValue::from_uint64(sequence).map_err(invalid)?;
+            created.headers = Some(HashMap::from([(key, value)]));
+        }
+        Ok(created)
+    }
+
+    /// Send messages to a topic in a single round-trip.
+    async fn send_messages(&self, topic: &str, messages: &mut [Message]) -> Result<()> {
         if messages.is_empty() {
             return Ok(());
         }
 
+        self.ensure_initialized().await?;
+
         let stream_id = Identifier::from_str_value(&self.stream_name)
             .map_err(|e| InfraError::Streaming(format!("Invalid stream name: {e}")))?;
-        let topic_id = Identifier::from_str_value(topic.as_str())
+        let topic_id = Identifier::from_str_value(topic)
             .map_err(|e| InfraError::Streaming(format!("Invalid topic name: {e}")))?;
 
         self.client
@@ -297,85 +396,612 @@ impl IggyPublisher {
 }
 
 #[async_trait]
-impl EventPublisher for IggyPublisher {
+impl MessageTransport for IggyTransport {
+    #[instrument(skip(self, payloads), fields(topic = %topic, count = payloads.len()))]
+    async fn send(&self, topic: &str, payloads: &[Bytes]) -> Result<()> {
+        let mut messages: Vec<Message> = payloads
+            .iter()
+            .map(|payload| Self::create_message(0, payload))
+            .collect();
+        self.send_messages(topic, &mut messages).await
+    }
+
+    #[instrument(skip(self, messages), fields(topic = %topic, count = messages.len()))]
+    async fn send_identified(&self, topic: &str, messages: &[IdentifiedMessage]) -> Result<()> {
+        let mut messages = messages
+            .iter()
+            .map(Self::create_identified_message)
+            .collect::<Result<Vec<Message>>>()?;
+        self.send_messages(topic, &mut messages).await
+    }
+
+    async fn close(&self) -> Result<()> {
+        if self.connected.load(Ordering::SeqCst) {
+            self.disconnect().await?;
+        }
+        Ok(())
+    }
+
+    fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::SeqCst)
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// DEAD LETTERS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Destination for batches that could not be delivered after all retries.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeadLetterSink {
+    /// Append one JSON record per message to a local file.
+    File(PathBuf),
+    /// Publish one JSON record per message to a dedicated topic.
+    Topic(String),
+}
+
+impl DeadLetterSink {
+    /// Select the sink configured in settings.
+    ///
+    /// A `dead_letter_path` takes precedence over `dead_letter_topic`.
+    #[must_use]
+    pub fn from_settings(settings: &IggySettings) -> Self {
+        settings.dead_letter_path.as_ref().map_or_else(
+            || Self::Topic(settings.dead_letter_topic.clone()),
+            |path| Self::File(PathBuf::from(path)),
+        )
+    }
+}
+
+/// A dead-lettered message with enough context to replay it.
+#[derive(Debug, Clone, Serialize)]
+struct DeadLetterRecord<'a> {
+    /// Topic the message was meant for.
+    topic: &'a str,
+    /// Number of send attempts made.
+    attempts: u32,
+    /// Error from the final attempt.
+    error: &'a str,
+    /// When the message was dead-lettered.
+    failed_at: DateTime<Utc>,
+    /// Original payload (JSON when possible, otherwise hex-encoded bytes).
+    payload: serde_json::Value,
+}
+
+impl DeadLetterRecord<'_> {
+    fn payload_value(payload: &[u8]) -> serde_json::Value {
+        serde_json::from_slice(payload)
+            .unwrap_or_else(|_| serde_json::Value::String(format!("0x{}", hex::encode(payload))))
+    }
+
+    fn to_bytes(&self) -> Result<Vec<u8>> {
+        serde_json::to_vec(self).map_err(|e| {
+            InfraError::Streaming(format!("Failed to serialize dead letter: {e}")).into()
+        })
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// PUBLISHER
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Delivery counters for the health endpoint.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct PublisherStats {
+    /// Messages successfully delivered to their topic.
+    pub published: u64,
+    /// Batch send attempts that were retried after a failure.
+    pub retried: u64,
+    /// Messages routed to the dead-letter sink.
+    pub dead_lettered: u64,
+    /// Messages currently waiting in the buffer.
+    pub buffered: usize,
+}
+
+/// Delivery counters of a publisher as `GET /health` reports them, with
+/// the thresholds past which it is degraded.
+#[derive(Clone)]
+pub struct PublisherHealth {
+    /// Reads the publisher's current counters.
+    stats: Arc<dyn Fn() -> PublisherStats + Send + Sync>,
+    /// Buffered messages past which the publisher is degraded.
+    max_buffered: usize,
+    /// Dead-lettered messages past which the publisher is degraded.
+    max_dead_lettered: u64,
+}
+
+impl PublisherHealth {
+    /// Report the counters of `publisher`, degraded past the `health_*`
+    /// thresholds of `settings`.
+    #[must_use]
+    pub fn new<T: MessageTransport + 'static>(
+        publisher: Arc<IggyPublisher<T>>,
+        settings: &IggySettings,
+    ) -> Self {
+        Self {
+            stats: Arc::new(move || publisher.stats()),
+            max_buffered: settings.health_max_buffered,
+            max_dead_lettered: settings.health_max_dead_lettered,
+        }
+    }
+
+    /// The publisher's current counters, and whether they are within the
+    /// thresholds.
+    #[must_use]
+    pub fn check(&self) -> (PublisherStats, bool) {
+        let stats = (self.stats)();
+        let healthy =
+            stats.buffered <= self.max_buffered && stats.dead_lettered <= self.max_dead_lettered;
+        (stats, healthy)
+    }
+}
+
+impl std::fmt::Debug for PublisherHealth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PublisherHealth")
+            .field("max_buffered", &self.max_buffered)
+            .field("max_dead_lettered", &self.max_dead_lettered)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A serialized message waiting to be sent.
+#[derive(Debug, Clone)]
+struct BufferedMessage {
+    topic: String,
+    payload: Bytes,
+}
+
+/// Batching and retry parameters derived from [`IggySettings`].
+#[derive(Debug, Clone, Copy)]
+struct BatchConfig {
+    max_batch_size: usize,
+    max_batch_delay: Duration,
+    max_retries: u32,
+    retry_backoff: Duration,
+}
+
+impl BatchConfig {
+    fn from_settings(settings: &IggySettings) -> Self {
+        Self {
+            max_batch_size: settings.max_batch_size.max(1),
+            max_batch_delay: settings.max_batch_delay(),
+            max_retries: settings.max_retries,
+            retry_backoff: settings.retry_backoff(),
+        }
+    }
+
+    /// Backoff before the given retry (1-based), doubling each time.
+    fn backoff(&self, retry: u32) -> Duration {
+        let factor = 2_u32.saturating_pow(retry.saturating_sub(1));
+        self.retry_backoff
+            .saturating_mul(factor)
+            .min(MAX_RETRY_BACKOFF)
+    }
+}
+
+/// Apache Iggy-based event publisher.
+///
+/// Buffers GHOSTNET events and publishes them to the appropriate topics in
+/// batches, retrying failed sends and dead-lettering batches that cannot be
+/// delivered. Events are published in every [wire schema](super::wire)
+/// version of their topic.
+///
+/// # Thread Safety
+///
+/// This type is `Send + Sync` and can be shared across tasks.
+pub struct IggyPublisher<T: MessageTransport = IggyTransport> {
+    /// Underlying message transport.
+    transport: T,
+    /// Batching and retry parameters.
+    config: BatchConfig,
+    /// Where undeliverable batches go.
+    dead_letter: DeadLetterSink,
+    /// Messages waiting to be sent, in publish order.
+    buffer: Mutex<Vec<BufferedMessage>>,
+    /// Serializes flushes so batches leave in publish order.
+    flush_lock: tokio::sync::Mutex<()>,
+    /// Messages delivered.
+    published: AtomicU64,
+    /// Retried send attempts.
+    retried: AtomicU64,
+    /// Messages dead-lettered.
+    dead_lettered: AtomicU64,
+    /// Schema versions the events are published in.
+    schemas: Arc<WireSchemas>,
+}
+
+impl<T: MessageTransport + std::fmt::Debug> std::fmt::Debug for IggyPublisher<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IggyPublisher")
+            .field("transport", &self.transport)
+            .field("dead_letter", &self.dead_letter)
+            .field("stats", &self.stats())
+            .finish_non_exhaustive()
+    }
+}
+
+impl IggyPublisher<IggyTransport> {
+    /// Create a new Iggy publisher from settings.
+    ///
+    /// This creates the client but does NOT connect. Call `connect()` to establish
+    /// the connection, or let it connect lazily on first send.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the client cannot be created.
+    pub fn new(settings: &IggySettings) -> Result<Self> {
+        let transport = IggyTransport::new(settings)?;
+        Ok(Self::with_transport(transport, settings))
+    }
+
+    /// Connect to the Iggy server.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if connection fails.
+    pub async fn connect(&self) -> Result<()> {
+        self.transport.connect().await
+    }
+
+    /// Disconnect from the Iggy server.
+    ///
+    /// Buffered messages are NOT flushed; use `shutdown()` for a graceful stop.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if disconnection fails.
+    pub async fn disconnect(&self) -> Result<()> {
+        self.transport.disconnect().await
+    }
+}
+
+impl<T: MessageTransport> IggyPublisher<T> {
+    /// Create a publisher over an arbitrary transport.
+    #[must_use]
+    pub fn with_transport(transport: T, settings: &IggySettings) -> Self {
+        Self {
+            transport,
+            config: BatchConfig::from_settings(settings),
+            dead_letter: DeadLetterSink::from_settings(settings),
+            buffer: Mutex::new(Vec::new()),
+            flush_lock: tokio::sync::Mutex::new(()),
+            published: AtomicU64::new(0),
+            retried: AtomicU64::new(0),
+            dead_lettered: AtomicU64::new(0),
+            schemas: Arc::default(),
+        }
+    }
+
+    /// Publish the schema versions of `schemas` instead of all of them.
+    #[must_use]
+    pub fn with_schemas(mut self, schemas: Arc<WireSchemas>) -> Self {
+        self.schemas = schemas;
+        self
+    }
+
+    /// Get delivery counters.
+    #[must_use]
+    pub fn stats(&self) -> PublisherStats {
+        PublisherStats {
+            published: self.published.load(Ordering::Relaxed),
+            retried: self.retried.load(Ordering::Relaxed),
+            dead_lettered: self.dead_lettered.load(Ordering::Relaxed),
+            buffered: self.buffer.lock().len(),
+        }
+    }
+
+    /// Flush the buffer and release the transport.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if buffered messages could be neither delivered
+    /// nor dead-lettered, or if the transport fails to close.
+    #[instrument(skip(self))]
+    pub async fn shutdown(&self) -> Result<()> {
+        self.flush_all().await?;
+        self.transport.close().await?;
+        info!(stats = ?self.stats(), "Iggy publisher shut down");
+        Ok(())
+    }
+
+    /// Spawn a task that flushes the buffer every `max_batch_delay`.
+    ///
+    /// When `shutdown` is cancelled the task performs a final flush via
+    /// [`Self::shutdown`] before exiting.
+    pub fn spawn_flush_task(self: &Arc<Self>, shutdown: CancellationToken) -> JoinHandle<()>
+    where
+        T: 'static,
+    {
+        let publisher = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(publisher.config.max_batch_delay);
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+            loop {
+                tokio::select! {
+                    () = shutdown.cancelled() => break,
+                    _ = ticker.tick() => {
+                        if let Err(e) = publisher.flush_all().await {
+                            warn!(error = %e, "Periodic flush failed");
+                        }
+                    }
+                }
+            }
+
+            if let Err(e) = publisher.shutdown().await {
+                error!(error = %e, "Failed to flush publisher on shutdown");
+            }
+        })
+    }
+
+    /// Messages of an event, one per schema version published.
+    fn encode(&self, event: &GhostnetEvent) -> Result<Vec<BufferedMessage>> {
+        let topic = Topic::for_event(event).as_str();
+        Ok(self
+            .schemas
+            .encode(event)?
+            .into_iter()
+            .map(|message| BufferedMessage {
+                topic: topic.to_string(),
+                payload: message.payload,
+            })
+            .collect())
+    }
+
+    /// Append messages to the buffer, returning whether a full batch is ready.
+    fn enqueue(&self, messages: impl IntoIterator<Item = BufferedMessage>) -> bool {
+        let mut buffer = self.buffer.lock();
+        buffer.extend(messages);
+        buffer.len() >= self.config.max_batch_size
+    }
+
+    /// Take up to `max_batch_size` messages from the front of the buffer.
+    ///
+    /// With `full_only`, returns nothing unless a full batch is available.
+    fn take_batch(&self, full_only: bool) -> Vec<BufferedMessage> {
+        let mut buffer = self.buffer.lock();
+        if full_only && buffer.len() < self.config.max_batch_size {
+            return Vec::new();
+        }
+        let count = buffer.len().min(self.config.max_batch_size);
+        buffer.drain(..count).collect()
+    }
+
+    /// Put messages that could be neither delivered nor dead-lettered back
+    /// at the front of the buffer, ahead of those published since.
+    fn requeue(&self, messages: Vec<BufferedMessage>) {
+        let mut buffer = self.buffer.lock();
+        buffer.splice(..0, messages);
+    }
+
+    /// Send every full batch currently in the buffer.
+    async fn flush_full_batches(&self) -> Result<()> {
+        let _guard = self.flush_lock.lock().await;
+        loop {
+            let batch = self.take_batch(true);
+            if batch.is_empty() {
+                return Ok(());
+            }
+            self.send_batch(batch).await?;
+        }
+    }
+
+    /// Send everything in the buffer, in batches of at most `max_batch_size`.
+    async fn flush_all(&self) -> Result<()> {
+        let _guard = self.flush_lock.lock().await;
+        loop {
+            let batch = self.take_batch(false);
+            if batch.is_empty() {
+                return Ok(());
+            }
+            self.send_batch(batch).await?;
+        }
+    }
+
+    /// Send a batch, one round-trip per topic, preserving per-topic order.
+    ///
+    /// Every topic group is attempted even if an earlier one fails; the first
+    /// error is returned, and the groups that failed are
+    /// [requeued](Self::requeue).
+    async fn send_batch(&self, batch: Vec<BufferedMessage>) -> Result<()> {
+        let mut order: Vec<String> = Vec::new();
+        let mut by_topic: HashMap<String, Vec<Bytes>> = HashMap::new();
+        for message in batch {
+            if !by_topic.contains_key(&message.topic) {
+                order.push(message.topic.clone());
+            }
+            by_topic
+                .entry(message.topic)
+                .or_default()
+                .push(message.payload);
+        }
+
+        let mut first_error = None;
+        let mut undelivered = Vec::new();
+        for topic in order {
+            let payloads = by_topic.remove(&topic).unwrap_or_default();
+            if let Err(e) = self.deliver(&topic, &payloads).await {
+                error!(
+                    error = %e,
+                    topic = %topic,
+                    count = payloads.len(),
+                    "Dead-lettering failed, keeping batch"
+                );
+                undelivered.extend(payloads.into_iter().map(|payload| BufferedMessage {
+                    topic: topic.clone(),
+                    payload,
+                }));
+                first_error.get_or_insert(e);
+            }
+        }
+        self.requeue(undelivered);
+
+        first_error.map_or(Ok(()), Err)
+    }
+
+    /// Send payloads to a topic, retrying with backoff and dead-lettering on exhaustion.
+    #[instrument(skip(self, payloads), fields(topic = %topic, count = payloads.len()))]
+    async fn deliver(&self, topic: &str, payloads: &[Bytes]) -> Result<()> {
+        let mut attempts = 0;
+        loop {
+            attempts += 1;
+            match self.transport.send(topic, payloads).await {
+                Ok(()) => {
+                    self.published
+                        .fetch_add(payloads.len() as u64, Ordering::Relaxed);
+                    obs::record_published(topic, payloads.len());
+                    return Ok(());
+                }
+                Err(e) if attempts <= self.config.max_retries => {
+                    self.retried.fetch_add(1, Ordering::Relaxed);
+                    let backoff = self.config.backoff(attempts);
+                    warn!(
+                        error = %e,
+                        attempt = attempts,
+                        backoff_ms = backoff.as_millis(),
+                        "Batch send failed, retrying"
+                    );
+                    tokio::time::sleep(backoff).await;
+                }
+                Err(e) => {
+                    error!(error = %e, attempts, "Batch send failed, dead-lettering");
+                    return self
+                        .dead_letter(topic, payloads, attempts, &e.to_string())
+                        .await;
+                }
+            }
+        }
+    }
+
+    /// Write an undeliverable batch to the dead-letter sink.
+    async fn dead_letter(
+        &self,
+        topic: &str,
+        payloads: &[Bytes],
+        attempts: u32,
+        error: &str,
+    ) -> Result<()> {
+        let failed_at = Utc::now();
+        let records = payloads
+            .iter()
+            .map(|payload| {
+                DeadLetterRecord {
+                    topic,
+                    attempts,
+                    error,
+                    failed_at,
+                    payload: DeadLetterRecord::payload_value(payload),
+                }
+                .to_bytes()
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        match &self.dead_letter {
+            DeadLetterSink::File(path) => {
+                let mut contents = Vec::new();
+                for record in records {
+                    contents.extend_from_slice(&record);
+                    contents.push(b'\n');
+                }
+                let mut file = tokio::fs::OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(path)
+                    .await
+                    .map_err(|e| {
+                        InfraError::Streaming(format!(
+                            "Failed to open dead-letter file {}: {e}",
+                            path.display()
+                        ))
+                    })?;
+                file.write_all(&contents).await.map_err(|e| {
+                    InfraError::Streaming(format!("Failed to write dead-letter file: {e}"))
+                })?;
+                file.flush().await.map_err(|e| {
+                    InfraError::Streaming(format!("Failed to flush dead-letter file: {e}"))
+                })?;
+            }
+            DeadLetterSink::Topic(dlq_topic) => {
+                let records: Vec<Bytes> = records.into_iter().map(Bytes::from).collect();
+                self.transport.send(dlq_topic, &records).await?;
+            }
+        }
+
+        self.dead_lettered
+            .fetch_add(payloads.len() as u64, Ordering::Relaxed);
+        obs::record_dead_lettered(topic, payloads.len());
+        warn!(topic = %topic, count = payloads.len(), sink = ?self.dead_letter, "Dead-lettered batch");
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<T: MessageTransport> EventPublisher for IggyPublisher<T> {
     #[instrument(skip(self, event), fields(event_type = %event.type_name()))]
     async fn publish(&self, event: &GhostnetEvent) -> Result<()> {
-        self.ensure_initialized().await?;
+        let messages = self.encode(event)?;
 
-        let topic = Topic::for_event(event);
-        let mut message = Self::create_message(event)?;
-
-        self.send_to_topic(topic, std::slice::from_mut(&mut message))
-            .await
+        if self.enqueue(messages) {
+            self.flush_full_batches().await?;
+        }
+        Ok(())
     }
 
     #[instrument(skip(self, payload), fields(topic = %topic, size = payload.len()))]
     async fn publish_to_topic(&self, topic: &str, payload: &[u8]) -> Result<()> {
-        self.ensure_initialized().await?;
-
-        let stream_id = Identifier::from_str_value(&self.stream_name)
-            .map_err(|e| InfraError::Streaming(format!("Invalid stream name: {e}")))?;
-        let topic_id = Identifier::from_str_value(topic)
-            .map_err(|e| InfraError::Streaming(format!("Invalid topic name: {e}")))?;
-
-        // Message payload length is capped at u32::MAX by Iggy protocol.
-        // Practical payloads are always << 4GB, so this cast is safe.
-        #[allow(clippy::cast_possible_truncation)]
-        let length = payload.len() as u32;
-        let mut message = Message {
-            id: 0,
-            length,
+        let message = BufferedMessage {
+            topic: topic.to_string(),
             payload: Bytes::copy_from_slice(payload),
-            headers: None,
         };
 
-        self.client
-            .send_messages(
-                &stream_id,
-                &topic_id,
-                &Partitioning::balanced(),
-                std::slice::from_mut(&mut message),
-            )
-            .await
-            .map_err(|e| InfraError::Streaming(format!("Failed to send message: {e}")))?;
-
+        if self.enqueue([message]) {
+            self.flush_full_batches().await?;
+        }
         Ok(())
     }
 
+    /// Buffer a batch of events.
+    ///
+    /// Either every event is buffered or, if any fails to serialize, none are.
+    /// Full batches are sent immediately; the remainder waits for the next flush.
     #[instrument(skip(self, events), fields(count = events.len()))]
     async fn publish_batch(&self, events: &[GhostnetEvent]) -> Result<()> {
-        use std::collections::HashMap;
-
         if events.is_empty() {
             return Ok(());
         }
 
-        self.ensure_initialized().await?;
-
-        // Group events by topic for efficient batching
-        let mut by_topic: HashMap<Topic, Vec<Message>> = HashMap::new();
-
+        let mut messages = Vec::with_capacity(events.len());
         for event in events {
-            let topic = Topic::for_event(event);
-            let message = Self::create_message(event)?;
-            by_topic.entry(topic).or_default().push(message);
+            messages.extend(self.encode(event)?);
         }
 
-        // Send each batch to its topic
-        for (topic, mut messages) in by_topic {
-            self.send_to_topic(topic, &mut messages).await?;
+        if self.enqueue(messages) {
+            self.flush_full_batches().await?;
         }
+        Ok(())
+    }
 
+    /// Send the messages right away, without retries or dead-lettering.
+    #[instrument(skip(self, messages), fields(topic = %topic, count = messages.len()))]
+    async fn publish_acknowledged(
+        &self,
+        topic: &str,
+        messages: &[IdentifiedMessage],
+    ) -> Result<()> {
+        self.transport.send_identified(topic, messages).await?;
+        self.published
+            .fetch_add(messages.len() as u64, Ordering::Relaxed);
+        obs::record_published(topic, messages.len());
         Ok(())
     }
 
     async fn flush(&self) -> Result<()> {
-        // Iggy doesn't have an explicit flush - messages are sent synchronously
-        Ok(())
+        self.flush_all().await
     }
 
     fn is_connected(&self) -> bool {
-        self.connected.load(Ordering::SeqCst)
+        self.transport.is_connected()
     }
 }
 
@@ -400,6 +1026,14 @@ impl EventPublisher for NoOpPublisher {
         Ok(())
     }
 
+    async fn publish_acknowledged(
+        &self,
+        _topic: &str,
+        _messages: &[IdentifiedMessage],
+    ) -> Result<()> {
+        Ok(())
+    }
+
     async fn flush(&self) -> Result<()> {
         Ok(())
     }
@@ -410,9 +1044,17 @@ impl EventPublisher for NoOpPublisher {
 }
 
 #[cfg(test)]
+#[allow(clippy::unwrap_used)]
 mod tests {
+    use std::collections::HashSet;
+    use std::sync::atomic::AtomicU32;
+
+    use alloy::primitives::{Address, U256};
+
     use super::*;
-    use crate::config::IggySettings;
+    use crate::config::{IggySettings, SchemaSettings};
+    use crate::types::enums::Level;
+    use crate::types::events::{EventMetadata, JackedInEvent, TransferEvent};
 
     fn test_settings() -> IggySettings {
         IggySettings {
@@ -422,6 +1064,98 @@ mod tests {
             replication_factor: 1,
             username: "iggy".to_string(),
             password: "iggy".to_string(),
+            max_batch_size: 3,
+            max_batch_delay_ms: 10,
+            max_retries: 2,
+            retry_backoff_ms: 1,
+            dead_letter_path: None,
+            dead_letter_topic: "dead-letter".to_string(),
+            health_max_buffered: 2,
+            health_max_dead_lettered: 1,
+        }
+    }
+
+    fn transfer_event() -> GhostnetEvent {
+        GhostnetEvent::Transfer(TransferEvent {
+            meta: EventMetadata {
+                block_number: 1000,
+                block_hash: [1u8; 32].into(),
+                tx_hash: [2u8; 32].into(),
+                tx_index: 0,
+                log_index: 0,
+                timestamp: Utc::now(),
+                contract: Address::ZERO,
+                chain_id: 6343,
+                tx_function: None,
+                tx_from: None,
+            },
+            from: Address::ZERO,
+            to: Address::repeat_byte(0x11),
+            value: U256::from(1000),
+        })
+    }
+
+    /// Transport that records sends and fails on configured topics.
+    #[derive(Debug, Default)]
+    struct MockTransport {
+        /// Successful sends as (topic, payloads).
+        sent: Mutex<Vec<(String, Vec<Bytes>)>>,
+        /// Topics whose sends always fail.
+        failing_topics: HashSet<String>,
+        /// Number of upcoming sends that fail regardless of topic.
+        fail_next: AtomicU32,
+        /// Total send attempts.
+        attempts: AtomicU32,
+        /// Whether `close` was called.
+        closed: AtomicBool,
+    }
+
+    impl MockTransport {
+        fn failing_on(topic: &str) -> Self {
+            Self {
+                failing_topics: HashSet::from([topic.to_string()]),
+                ..Self::default()
+            }
+        }
+
+        fn sent(&self) -> Vec<(String, Vec<Bytes>)> {
+            self.sent.lock().clone()
+        }
+
+        fn sent_count(&self, topic: &str) -> usize {
+            self.sent
+                .lock()
+                .iter()
+                .filter(|(t, _)| t == topic)
+                .map(|(_, p)| p.len())
+                .sum()
+        }
+    }
+
+    #[async_trait]
+    impl MessageTransport for MockTransport {
+        async fn send(&self, topic: &str, payloads: &[Bytes]) -> Result<()> {
+            self.attempts.fetch_add(1, Ordering::SeqCst);
+            let forced = self
+                .fail_next
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                .is_ok();
+            if forced || self.failing_topics.contains(topic) {
+                return Err(InfraError::Streaming("mock send failure".into()).into());
+            }
+            self.sent
+                .lock()
+                .push((topic.to_string(), payloads.to_vec()));
+            Ok(())
+        }
+
+        async fn close(&self) -> Result<()> {
+            self.closed.store(true, Ordering::SeqCst);
+            Ok(())
+        }
+
+        fn is_connected(&self) -> bool {
+            !self.closed.load(Ordering::SeqCst)
         }
     }
 
@@ -433,11 +1167,363 @@ mod tests {
         assert!(result.is_ok());
 
         let publisher = result.unwrap();
-        let debug_str = format!("{:?}", publisher);
+        let debug_str = format!("{publisher:?}");
         assert!(debug_str.contains("IggyPublisher"));
         assert!(debug_str.contains("ghostnet-test"));
     }
 
+    #[test]
+    fn dead_letter_sink_prefers_file() {
+        let mut settings = test_settings();
+        assert_eq!(
+            DeadLetterSink::from_settings(&settings),
+            DeadLetterSink::Topic("dead-letter".into())
+        );
+
+        settings.dead_letter_path = Some("/tmp/dlq.jsonl".into());
+        assert_eq!(
+            DeadLetterSink::from_settings(&settings),
+            DeadLetterSink::File(PathBuf::from("/tmp/dlq.jsonl"))
+        );
+    }
+
+    #[test]
+    fn backoff_doubles_and_caps() {
+        let mut settings = test_settings();
+        settings.retry_backoff_ms = 100;
+        let config = BatchConfig::from_settings(&settings);
+
+        assert_eq!(config.backoff(1), Duration::from_millis(100));
+        assert_eq!(config.backoff(2), Duration::from_millis(200));
+        assert_eq!(config.backoff(3), Duration::from_millis(400));
+        assert_eq!(config.backoff(30), MAX_RETRY_BACKOFF);
+    }
+
+    #[tokio::test]
+    async fn buffers_until_batch_is_full() {
+        let publisher = IggyPublisher::with_transport(MockTransport::default(), &test_settings());
+
+        publisher.publish(&transfer_event()).await.unwrap();
+        publisher.publish(&transfer_event()).await.unwrap();
+        assert!(publisher.transport.sent().is_empty());
+        assert_eq!(publisher.stats().buffered, 2);
+
+        publisher.publish(&transfer_event()).await.unwrap();
+        let sent = publisher.transport.sent();
+        assert_eq!(sent.len(), 1, "one round-trip for a full batch");
+        assert_eq!(sent[0].0, "token");
+        assert_eq!(sent[0].1.len(), 3);
+        assert_eq!(publisher.stats().published, 3);
+        assert_eq!(publisher.stats().buffered, 0);
+    }
+
+    #[tokio::test]
+    async fn publish_batch_splits_at_max_batch_size() {
+        let publisher = IggyPublisher::with_transport(MockTransport::default(), &test_settings());
+        let events = vec![transfer_event(); 7];
+
+        publisher.publish_batch(&events).await.unwrap();
+
+        // Two full batches sent, remainder waits for the next flush
+        let sizes: Vec<usize> = publisher
+            .transport
+            .sent()
+            .iter()
+            .map(|(_, p)| p.len())
+            .collect();
+        assert_eq!(sizes, vec![3, 3]);
+        assert_eq!(publisher.stats().buffered, 1);
+
+        publisher.flush().await.unwrap();
+        assert_eq!(publisher.transport.sent_count("token"), 7);
+    }
+
+    #[tokio::test]
+    async fn events_are_buffered_in_every_schema_version() {
+        let jacked_in = GhostnetEvent::JackedIn(JackedInEvent {
+            meta: transfer_event().metadata().clone(),
+            user: Address::ZERO,
+            amount: U256::from(1),
+            level: Level::Vault,
+            new_total: U256::from(1),
+        });
+
+        let publisher = IggyPublisher::with_transport(MockTransport::default(), &test_settings());
+        publisher.publish(&jacked_in).await.unwrap();
+        assert_eq!(publisher.stats().buffered, 2);
+
+        let schemas = WireSchemas::new(&SchemaSettings {
+            versions: HashMap::from([("positions".to_string(), vec![2])]),
+        });
+        let publisher = IggyPublisher::with_transport(MockTransport::default(), &test_settings())
+            .with_schemas(Arc::new(schemas));
+        publisher.publish(&jacked_in).await.unwrap();
+        publisher.flush().await.unwrap();
+        let sent = publisher.transport.sent();
+        let envelope: serde_json::Value = serde_json::from_slice(&sent[0].1[0]).unwrap();
+        assert_eq!(envelope["schema_version"], 2);
+        assert_eq!(envelope["topic"], "positions");
+    }
+
+    #[tokio::test]
+    async fn groups_batch_by_topic() {
+        let publisher = IggyPublisher::with_transport(MockTransport::default(), &test_settings());
+
+        publisher.publish_to_topic("scans", b"{}").await.unwrap();
+        publisher.publish(&transfer_event()).await.unwrap();
+        publisher.publish_to_topic("scans", b"{}").await.unwrap();
+
+        let topics: Vec<String> = publisher
+            .transport
+            .sent()
+            .into_iter()
+            .map(|(t, _)| t)
+            .collect();
+        assert_eq!(topics, vec!["scans".to_string(), "token".to_string()]);
+        assert_eq!(publisher.transport.sent_count("scans"), 2);
+    }
+
+    #[tokio::test]
+    async fn retries_transient_failures() {
+        let transport = MockTransport::default();
+        transport.fail_next.store(2, Ordering::SeqCst);
+        let publisher = IggyPublisher::with_transport(transport, &test_settings());
+
+        publisher
+            .publish_to_topic("positions", b"{}")
+            .await
+            .unwrap();
+        publisher.flush().await.unwrap();
+
+        let stats = publisher.stats();
+        assert_eq!(stats.published, 1);
+        assert_eq!(stats.retried, 2);
+        assert_eq!(stats.dead_lettered, 0);
+    }
+
+    #[tokio::test]
+    async fn acknowledged_publish_skips_buffer_and_dead_letters() {
+        let publisher =
+            IggyPublisher::with_transport(MockTransport::failing_on("token"), &test_settings());
+        let messages = [IdentifiedMessage {
+            id: 7,
+            payload: Bytes::from_static(b"{}"),
+            sequence: Some(1),
+        }];
+
+        publisher
+            .publish_acknowledged("positions", &messages)
+            .await
+            .unwrap();
+        assert_eq!(publisher.transport.sent_count("positions"), 1);
+
+        // Failures go back to the caller
+        assert!(
+            publisher
+                .publish_acknowledged("token", &messages)
+                .await
+                .is_err()
+        );
+        let stats = publisher.stats();
+        assert_eq!(stats.published, 1);
+        assert_eq!(stats.retried, 0);
+        assert_eq!(stats.dead_lettered, 0);
+        assert_eq!(stats.buffered, 0);
+    }
+
+    #[tokio::test]
+    async fn retry_exhaustion_routes_to_dead_letter_topic() {
+        let publisher =
+            IggyPublisher::with_transport(MockTransport::failing_on("token"), &test_settings());
+
+        publisher.publish(&transfer_event()).await.unwrap();
+        publisher.publish(&transfer_event()).await.unwrap();
+        publisher.flush().await.unwrap();
+
+        // 1 initial attempt + 2 retries, then one DLQ send
+        assert_eq!(publisher.transport.attempts.load(Ordering::SeqCst), 4);
+        assert_eq!(publisher.transport.sent_count("dead-letter"), 2);
+
+        let stats = publisher.stats();
+        assert_eq!(stats.published, 0);
+        assert_eq!(stats.retried, 2);
+        assert_eq!(stats.dead_lettered, 2);
+
+        let sent = publisher.transport.sent();
+        let record: serde_json::Value = serde_json::from_slice(&sent[0].1[0]).unwrap();
+        assert_eq!(record["topic"], "token");
+        assert_eq!(record["attempts"], 3);
+        assert_eq!(record["payload"]["event"]["type"], "Transfer");
+    }
+
+    #[tokio::test]
+    async fn retry_exhaustion_routes_to_dead_letter_file() {
+        let path =
+            std::env::temp_dir().join(format!("ghostnet-dlq-{}.jsonl", uuid::Uuid::new_v4()));
+        let mut settings = test_settings();
+        settings.dead_letter_path = Some(path.to_string_lossy().into_owned());
+        let publisher =
+            IggyPublisher::with_transport(MockTransport::failing_on("scans"), &settings);
+
+        publisher
+            .publish_to_topic("scans", b"{\"n\":1}")
+            .await
+            .unwrap();
+        publisher
+            .publish_to_topic("scans", &[0xde, 0xad])
+            .await
+            .unwrap();
+        publisher.flush().await.unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let records: Vec<serde_json::Value> = contents
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0]["payload"]["n"], 1);
+        assert_eq!(records[1]["payload"], "0xdead");
+        assert_eq!(publisher.stats().dead_lettered, 2);
+    }
+
+    #[tokio::test]
+    async fn failing_dead_letter_sink_surfaces_error() {
+        let mut transport = MockTransport::failing_on("token");
+        transport.failing_topics.insert("dead-letter".into());
+        let publisher = IggyPublisher::with_transport(transport, &test_settings());
+
+        publisher.publish(&transfer_event()).await.unwrap();
+        assert!(publisher.flush().await.is_err());
+        assert_eq!(publisher.stats().dead_lettered, 0);
+        assert_eq!(publisher.stats().buffered, 1, "the batch is kept");
+    }
+
+    #[tokio::test]
+    async fn batch_is_kept_until_topic_or_dead_letter_topic_recovers() {
+        let publisher = IggyPublisher::with_transport(MockTransport::default(), &test_settings());
+        publisher
+            .publish_to_topic("scans", b"{\"n\":1}")
+            .await
+            .unwrap();
+        publisher
+            .publish_to_topic("token", b"{\"n\":2}")
+            .await
+            .unwrap();
+
+        // Both topics and the dead-letter topic are down: 1 attempt and 2
+        // retries per topic, each followed by a failed dead-letter send
+        publisher.transport.fail_next.store(8, Ordering::SeqCst);
+        assert!(publisher.flush().await.is_err());
+        assert_eq!(publisher.stats().buffered, 2);
+        assert!(publisher.transport.sent().is_empty());
+
+        // Published meanwhile, behind the kept batch
+        publisher
+            .publish_to_topic("scans", b"{\"n\":3}")
+            .await
+            .unwrap();
+        publisher.flush().await.unwrap();
+
+        let stats = publisher.stats();
+        assert_eq!((stats.published, stats.dead_lettered, stats.buffered), (3, 0, 0));
+        let sent = publisher.transport.sent();
+        assert_eq!(sent[0].0, "scans");
+        assert_eq!(
+            sent[0].1,
+            [Bytes::from_static(b"{\"n\":1}"), Bytes::from_static(b"{\"n\":3}")]
+        );
+        assert_eq!(sent[1].0, "token");
+    }
+
+    #[tokio::test]
+    async fn health_degrades_past_thresholds() {
+        let publisher = Arc::new(IggyPublisher::with_transport(
+            MockTransport::failing_on("scans"),
+            &test_settings(),
+        ));
+        let health = PublisherHealth::new(Arc::clone(&publisher), &test_settings());
+        assert!(health.check().1);
+
+        // Two dead-lettered messages are past the one allowed
+        publisher.publish_to_topic("scans", b"1").await.unwrap();
+        publisher.flush().await.unwrap();
+        assert!(health.check().1);
+        publisher.publish_to_topic("scans", b"2").await.unwrap();
+        publisher.flush().await.unwrap();
+        let (stats, healthy) = health.check();
+        assert_eq!(stats.dead_lettered, 2);
+        assert!(!healthy);
+
+        // As are three buffered messages past two
+        let settings = IggySettings {
+            max_batch_size: 10,
+            ..test_settings()
+        };
+        let publisher = Arc::new(IggyPublisher::with_transport(
+            MockTransport::default(),
+            &settings,
+        ));
+        let health = PublisherHealth::new(Arc::clone(&publisher), &settings);
+        publisher.publish_to_topic("scans", b"1").await.unwrap();
+        publisher.publish_to_topic("scans", b"2").await.unwrap();
+        assert!(health.check().1);
+        publisher.publish_to_topic("scans", b"3").await.unwrap();
+        assert_eq!(health.check(), (publisher.stats(), false));
+    }
+
+    #[tokio::test]
+    async fn shutdown_flushes_buffer() {
+        let publisher = IggyPublisher::with_transport(MockTransport::default(), &test_settings());
+
+        publisher.publish(&transfer_event()).await.unwrap();
+        publisher.publish(&transfer_event()).await.unwrap();
+        assert!(publisher.transport.sent().is_empty());
+
+        publisher.shutdown().await.unwrap();
+        assert_eq!(publisher.transport.sent_count("token"), 2);
+        assert!(publisher.transport.closed.load(Ordering::SeqCst));
+        assert!(!publisher.is_connected());
+    }
+
+    #[tokio::test]
+    async fn flush_task_sends_after_delay_and_on_cancel() {
+        let mut settings = test_settings();
+        settings.max_batch_delay_ms = 60_000;
+        let publisher = Arc::new(IggyPublisher::with_transport(
+            MockTransport::default(),
+            &settings,
+        ));
+        let shutdown = CancellationToken::new();
+        let handle = publisher.spawn_flush_task(shutdown.clone());
+
+        // Let the immediate first tick pass before buffering
+        tokio::task::yield_now().await;
+        publisher.publish(&transfer_event()).await.unwrap();
+
+        shutdown.cancel();
+        handle.await.unwrap();
+        assert_eq!(publisher.transport.sent_count("token"), 1);
+        assert!(publisher.transport.closed.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn flush_task_sends_partial_batch_on_timer() {
+        let publisher = Arc::new(IggyPublisher::with_transport(
+            MockTransport::default(),
+            &test_settings(),
+        ));
+        let shutdown = CancellationToken::new();
+        let handle = publisher.spawn_flush_task(shutdown.clone());
+
+        publisher.publish(&transfer_event()).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(publisher.transport.sent_count("token"), 1);
+
+        shutdown.cancel();
+        handle.await.unwrap();
+    }
+
     #[test]
     fn noop_publisher_is_always_connected() {
         let publisher = NoOpPublisher;
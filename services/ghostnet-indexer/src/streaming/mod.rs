@@ -35,6 +35,7 @@
 //! | `system` | SystemResetTriggered, EmissionsDistributed, WeightsUpdated, TokensClaimed | System events |
 //! | `token` | Transfer, TaxBurned, TaxCollected, TaxExclusionSet | Token events |
 //! | `fees` | TollCollected, BuybackExecuted, OperationsWithdrawn | Fee events |
+//! | `derived` | RoundClosingSoon, RoundAwaitingResolution, BoostExpired | Countdowns and nudges |
 //!
 //! # Usage
 //!
@@ -53,10 +54,56 @@
 //!
 //! // Batch publish
 //! publisher.publish_batch(&events).await?;
+//!
+//! // Flush buffered messages and disconnect
+//! publisher.shutdown().await?;
 //! ```
+//!
+//! Messages are buffered and sent in batches; run
+//! [`IggyPublisher::spawn_flush_task`] so partial batches are sent within
+//! `max_batch_delay_ms`. Batches that still fail after `max_retries` go to the
+//! configured dead-letter sink.
+//!
+//! # Outbox
+//!
+//! Events that must not be lost or published for rolled back changes are
+//! written to the event outbox with the state change instead, and published by
+//! the [`OutboxRelay`] with [`EventPublisher::publish_acknowledged`]. Position
+//! events take this path when `outbox.enabled` is set; the buffered path above
+//! remains for best-effort publishing.
+//!
+//! # Sequences
+//!
+//! Wrapped in a [`SequencedPublisher`], acknowledged messages get the next
+//! sequence of their topic from the [`EventLog`], sent to Iggy in the
+//! [`SEQUENCE_HEADER`] header and delivered to the WebSocket API with it.
+//! The log keeps the published events for a while, so reconnecting
+//! WebSocket clients can resume from the last sequence they saw.
+//!
+//! # Wire schemas
+//!
+//! Events are not published as the internal [`GhostnetEvent`] but as the
+//! versioned DTOs of the [`wire`] module, in an [`Envelope`] carrying the
+//! schema version, topic, sequence and timestamp. While a topic publishes
+//! more than one version (`schemas.versions`), every event goes out once
+//! per version; see [`WireSchemas`].
+//!
+//! [`GhostnetEvent`]: crate::types::events::GhostnetEvent
+//! [`EventPublisher::publish_acknowledged`]: crate::ports::EventPublisher::publish_acknowledged
 
+mod event_log;
 mod iggy_publisher;
+mod outbox_relay;
 mod topics;
+pub mod wire;
 
-pub use iggy_publisher::{IggyPublisher, NoOpPublisher};
+#[cfg(test)]
+pub(crate) use event_log::tests::MemoryEventLog;
+pub use event_log::{EventLog, SequencedPublisher, Subscription, SubscriptionItem};
+pub use iggy_publisher::{
+    DeadLetterSink, IggyPublisher, IggyTransport, MessageTransport, NoOpPublisher,
+    PublisherHealth, PublisherStats, SEQUENCE_HEADER,
+};
+pub use outbox_relay::OutboxRelay;
 pub use topics::{STREAM_NAME, Topic, TopicConfig};
+pub use wire::{Envelope, WireSchemas};
@@ -0,0 +1,710 @@
+//! Relay from the event outbox to the streaming system.
+//!
+//! Handlers write their events to the outbox in the same transaction as the
+//! state change (see [`EventOutboxStore`]). The [`OutboxRelay`] polls the
+//! unpublished entries, publishes them through
+//! [`EventPublisher::publish_acknowledged`] and marks them published once the
+//! streaming system accepted them.
+//!
+//! ```text
+//! Handler ──▶ tx { state change + event_outbox row } ──▶ commit
+//!                                                          │
+//!              OutboxRelay ◀── fetch_unpublished ◀─────────┘
+//!                   │
+//!                   ├──▶ publish_acknowledged (message ID = outbox ID)
+//!                   └──▶ mark_published / record_publish_failure
+//! ```
+//!
+//! Entries are published in the [wire schemas](super::wire), once per
+//! version published for their topic. Version 1 keeps the outbox ID as the
+//! message ID; the other versions derive theirs from it.
+//!
+//! # Delivery Semantics
+//!
+//! Exactly the committed events are published: an event whose transaction
+//! rolled back was never in the outbox, and one committed before a crash is
+//! still unpublished after it. An event can be published twice if the relay
+//! stops between publishing and marking it, always with the same message ID,
+//! so consumers (or Iggy's message deduplication) see it once.
+//!
+//! # Ordering
+//!
+//! Entries are published in ID order per aggregate (position address, round
+//! ID, ...). A failed publish is retried with exponential backoff, and holds
+//! back the later entries of its aggregates until it succeeds; other
+//! aggregates keep flowing.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, instrument, warn};
+
+use super::WireSchemas;
+use crate::config::OutboxSettings;
+use crate::error::{InfraError, Result};
+use crate::obs;
+use crate::ports::{EventOutboxStore, EventPublisher, IdentifiedMessage};
+use crate::types::entities::OutboxRecord;
+use crate::types::events::GhostnetEvent;
+
+/// Interval between deletions of old published entries.
+const PURGE_INTERVAL: Duration = Duration::from_secs(3600);
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// OUTBOX RELAY
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Publishes committed events from the outbox.
+///
+/// Run one relay per outbox.
+#[derive(Debug)]
+pub struct OutboxRelay<O, P> {
+    /// Outbox the events are read from.
+    outbox: Arc<O>,
+    /// Publisher the events are sent through.
+    publisher: Arc<P>,
+    /// Most entries published per poll.
+    batch_size: u32,
+    /// Interval between polls.
+    poll_interval: Duration,
+    /// Delay before the first retry of a failed entry.
+    retry_backoff: Duration,
+    /// Upper bound for the retry delay.
+    max_retry_backoff: Duration,
+    /// How long published entries are kept.
+    retention: Duration,
+    /// Schema versions the events are published in.
+    schemas: Arc<WireSchemas>,
+    /// Chain the outbox depth is reported for.
+    chain_id: u64,
+}
+
+impl<O: EventOutboxStore, P: EventPublisher> OutboxRelay<O, P> {
+    /// Create a new relay.
+    #[must_use]
+    pub fn new(outbox: Arc<O>, publisher: Arc<P>, settings: &OutboxSettings) -> Self {
+        Self {
+            outbox,
+            publisher,
+            batch_size: settings.batch_size.max(1),
+            poll_interval: settings.poll_interval(),
+            retry_backoff: settings.retry_backoff(),
+            max_retry_backoff: settings.max_retry_backoff(),
+            retention: settings.retention(),
+            schemas: Arc::default(),
+            chain_id: 0,
+        }
+    }
+
+    /// Publish the schema versions of `schemas` instead of all of them.
+    #[must_use]
+    pub fn with_schemas(mut self, schemas: Arc<WireSchemas>) -> Self {
+        self.schemas = schemas;
+        self
+    }
+
+    /// Report the outbox depth under `chain_id`.
+    #[must_use]
+    pub const fn with_chain_id(mut self, chain_id: u64) -> Self {
+        self.chain_id = chain_id;
+        self
+    }
+
+    /// Publish the entries due at `now`, up to one batch, returning how many
+    /// were fetched.
+    ///
+    /// Consecutive entries of one topic are published together. A run that
+    /// fails is scheduled for a retry, and later entries of its aggregates
+    /// are left for the next poll.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the outbox cannot be read or updated. Entries
+    /// published but not marked are published again by the next poll.
+    #[instrument(skip(self))]
+    pub async fn relay_once(&self, now: DateTime<Utc>) -> Result<usize> {
+        let records = self.outbox.fetch_unpublished(self.batch_size, now).await?;
+        let mut blocked: HashSet<&str> = HashSet::new();
+        let mut run: Vec<&OutboxRecord> = Vec::new();
+
+        for record in &records {
+            if blocked.contains(record.event.aggregate.as_str()) {
+                continue;
+            }
+            if run
+                .first()
+                .is_some_and(|first| first.event.topic != record.event.topic)
+            {
+                self.publish_run(&run, now, &mut blocked).await?;
+                run.clear();
+                // The run just failed may have blocked this entry's aggregate
+                if blocked.contains(record.event.aggregate.as_str()) {
+                    continue;
+                }
+            }
+            run.push(record);
+        }
+        if !run.is_empty() {
+            self.publish_run(&run, now, &mut blocked).await?;
+        }
+
+        obs::set_outbox_depth(self.chain_id, self.outbox.outbox_depth().await?);
+        Ok(records.len())
+    }
+
+    /// Publish a run of entries of one topic.
+    ///
+    /// On failure, including entries that cannot be encoded, the entries are
+    /// scheduled for a retry and their aggregates added to `blocked`.
+    async fn publish_run<'a>(
+        &self,
+        run: &[&'a OutboxRecord],
+        now: DateTime<Utc>,
+        blocked: &mut HashSet<&'a str>,
+    ) -> Result<()> {
+        let Some(first) = run.first() else {
+            return Ok(());
+        };
+        let topic = first.event.topic.as_str();
+        let published = match self.encode(run) {
+            Ok(messages) => self.publisher.publish_acknowledged(topic, &messages).await,
+            Err(e) => Err(e),
+        };
+
+        match published {
+            Ok(()) => {
+                let ids: Vec<i64> = run.iter().map(|record| record.id).collect();
+                self.outbox.mark_published(&ids, now).await?;
+                debug!(topic, count = ids.len(), "Published outbox events");
+            }
+            Err(e) => {
+                warn!(topic, count = run.len(), error = %e, "Publishing outbox events failed");
+                obs::record_outbox_publish_failures(topic, run.len());
+                let error = e.to_string();
+                for record in run {
+                    let retry_at = now + self.backoff(record.attempts);
+                    self.outbox
+                        .record_publish_failure(record.id, &error, retry_at)
+                        .await?;
+                    blocked.insert(record.event.aggregate.as_str());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Messages of a run of entries, in every schema version published.
+    fn encode(&self, run: &[&OutboxRecord]) -> Result<Vec<IdentifiedMessage>> {
+        let mut messages = Vec::with_capacity(run.len());
+        for record in run {
+            let event: GhostnetEvent =
+                serde_json::from_slice(&record.event.payload).map_err(InfraError::Serialization)?;
+            let id = u128::from(record.id.unsigned_abs());
+            let encoded = self.schemas.encode(&event)?;
+            messages.extend(encoded.into_iter().map(|message| message.identified(id)));
+        }
+        Ok(messages)
+    }
+
+    /// Delay before retrying an entry that failed `attempts` times before.
+    fn backoff(&self, attempts: u32) -> Duration {
+        let factor = 2_u32.saturating_pow(attempts);
+        self.retry_backoff
+            .saturating_mul(factor)
+            .min(self.max_retry_backoff)
+    }
+
+    /// Delete entries published longer than the retention before `now`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the outbox cannot be updated.
+    pub async fn purge(&self, now: DateTime<Utc>) -> Result<u64> {
+        let retention = chrono::Duration::from_std(self.retention).unwrap_or(chrono::Duration::MAX);
+        let purged = self.outbox.purge_published(now - retention).await?;
+        if purged > 0 {
+            debug!(purged, "Purged published outbox events");
+        }
+        Ok(purged)
+    }
+
+    /// Spawn the background relay task.
+    ///
+    /// Polls every `poll_interval`, right away again while full batches are
+    /// fetched, and purges old entries every hour. When `shutdown` is
+    /// cancelled the task relays once more, so events committed before the
+    /// shutdown go out before the publisher closes.
+    pub fn spawn_relay_task(self: &Arc<Self>, shutdown: CancellationToken) -> JoinHandle<()>
+    where
+        O: 'static,
+        P: 'static,
+    {
+        let relay = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(relay.poll_interval);
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            let mut purge_ticker = tokio::time::interval(PURGE_INTERVAL);
+            purge_ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+            loop {
+                tokio::select! {
+                    () = shutdown.cancelled() => break,
+                    _ = ticker.tick() => relay.drain().await,
+                    _ = purge_ticker.tick() => {
+                        if let Err(e) = relay.purge(Utc::now()).await {
+                            warn!(error = %e, "Purging outbox events failed");
+                        }
+                    }
+                }
+            }
+
+            relay.drain().await;
+            info!("Outbox relay stopped");
+        })
+    }
+
+    /// Relay until a poll fetches less than a full batch.
+    async fn drain(&self) {
+        loop {
+            match self.relay_once(Utc::now()).await {
+                Ok(fetched) if fetched >= self.batch_size as usize => {}
+                Ok(_) => return,
+                Err(e) => {
+                    warn!(error = %e, "Relaying outbox events failed");
+                    return;
+                }
+            }
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::significant_drop_tightening)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use async_trait::async_trait;
+    use parking_lot::Mutex;
+
+    use super::*;
+    use alloy::primitives::{Address, B256, U256};
+
+    use crate::config::SchemaSettings;
+    use crate::types::entities::OutboxEvent;
+    use crate::types::enums::Level;
+    use crate::types::events::{EventMetadata, JackedInEvent, TransferEvent};
+
+    /// Stored outbox entry.
+    #[derive(Debug, Clone)]
+    struct Row {
+        record: OutboxRecord,
+        next_attempt_at: Option<DateTime<Utc>>,
+        published_at: Option<DateTime<Utc>>,
+    }
+
+    /// In-memory outbox following the store's contract.
+    #[derive(Debug, Default)]
+    struct MemoryOutbox {
+        rows: Mutex<Vec<Row>>,
+        /// Number of upcoming `mark_published` calls that fail.
+        fail_marks: AtomicU32,
+    }
+
+    impl MemoryOutbox {
+        /// Commit a transaction's events.
+        fn commit(&self, events: &[OutboxEvent]) {
+            let mut rows = self.rows.lock();
+            for event in events {
+                let id = i64::try_from(rows.len()).unwrap() + 1;
+                rows.push(Row {
+                    record: OutboxRecord {
+                        id,
+                        event: event.clone(),
+                        attempts: 0,
+                        created_at: Utc::now(),
+                    },
+                    next_attempt_at: None,
+                    published_at: None,
+                });
+            }
+        }
+    }
+
+    #[async_trait]
+    impl EventOutboxStore for MemoryOutbox {
+        async fn enqueue_events(&self, events: &[OutboxEvent]) -> Result<()> {
+            self.commit(events);
+            Ok(())
+        }
+
+        async fn fetch_unpublished(
+            &self,
+            limit: u32,
+            now: DateTime<Utc>,
+        ) -> Result<Vec<OutboxRecord>> {
+            let rows = self.rows.lock();
+            let mut waiting: HashSet<&str> = HashSet::new();
+            let mut due = Vec::new();
+            for row in rows.iter().filter(|row| row.published_at.is_none()) {
+                let aggregate = row.record.event.aggregate.as_str();
+                if row.next_attempt_at.is_some_and(|at| at > now) {
+                    waiting.insert(aggregate);
+                } else if !waiting.contains(aggregate) {
+                    due.push(row.record.clone());
+                }
+            }
+            due.truncate(limit as usize);
+            Ok(due)
+        }
+
+        async fn mark_published(&self, ids: &[i64], at: DateTime<Utc>) -> Result<()> {
+            let failed = self
+                .fail_marks
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                .is_ok();
+            if failed {
+                return Err(InfraError::Internal("injected failure".into()).into());
+            }
+            for row in self.rows.lock().iter_mut() {
+                if ids.contains(&row.record.id) {
+                    row.published_at = Some(at);
+                    row.next_attempt_at = None;
+                }
+            }
+            Ok(())
+        }
+
+        async fn record_publish_failure(
+            &self,
+            id: i64,
+            _error: &str,
+            retry_at: DateTime<Utc>,
+        ) -> Result<()> {
+            for row in self.rows.lock().iter_mut() {
+                if row.record.id == id {
+                    row.record.attempts += 1;
+                    row.next_attempt_at = Some(retry_at);
+                }
+            }
+            Ok(())
+        }
+
+        async fn purge_published(&self, before: DateTime<Utc>) -> Result<u64> {
+            let mut rows = self.rows.lock();
+            let count = rows.len();
+            rows.retain(|row| row.published_at.is_none_or(|at| at >= before));
+            Ok((count - rows.len()) as u64)
+        }
+
+        async fn outbox_depth(&self) -> Result<u64> {
+            let rows = self.rows.lock();
+            Ok(rows.iter().filter(|row| row.published_at.is_none()).count() as u64)
+        }
+    }
+
+    /// Publisher recording delivered message IDs per topic.
+    #[derive(Debug, Default)]
+    struct RecordingPublisher {
+        /// Delivered messages as (topic, ID), in delivery order.
+        delivered: Mutex<Vec<(String, u128)>>,
+        /// Remaining failures per topic.
+        failures: Mutex<HashMap<String, u32>>,
+    }
+
+    impl RecordingPublisher {
+        fn fail(&self, topic: &str, times: u32) {
+            self.failures.lock().insert(topic.to_string(), times);
+        }
+
+        fn delivered_ids(&self) -> Vec<u128> {
+            self.delivered.lock().iter().map(|(_, id)| *id).collect()
+        }
+    }
+
+    #[async_trait]
+    impl EventPublisher for RecordingPublisher {
+        async fn publish(&self, _event: &crate::types::events::GhostnetEvent) -> Result<()> {
+            Ok(())
+        }
+
+        async fn publish_to_topic(&self, _topic: &str, _payload: &[u8]) -> Result<()> {
+            Ok(())
+        }
+
+        async fn publish_batch(
+            &self,
+            _events: &[crate::types::events::GhostnetEvent],
+        ) -> Result<()> {
+            Ok(())
+        }
+
+        async fn publish_acknowledged(
+            &self,
+            topic: &str,
+            messages: &[IdentifiedMessage],
+        ) -> Result<()> {
+            if let Some(remaining) = self.failures.lock().get_mut(topic)
+                && *remaining > 0
+            {
+                *remaining -= 1;
+                return Err(InfraError::Streaming("injected failure".into()).into());
+            }
+            let mut delivered = self.delivered.lock();
+            delivered.extend(messages.iter().map(|m| (topic.to_string(), m.id)));
+            Ok(())
+        }
+
+        async fn flush(&self) -> Result<()> {
+            Ok(())
+        }
+
+        fn is_connected(&self) -> bool {
+            true
+        }
+    }
+
+    fn meta(block: u64) -> EventMetadata {
+        EventMetadata {
+            block_number: block,
+            block_hash: B256::ZERO,
+            tx_hash: B256::ZERO,
+            tx_index: 0,
+            log_index: 0,
+            timestamp: Utc::now(),
+            contract: Address::ZERO,
+            chain_id: 6343,
+            tx_function: None,
+            tx_from: None,
+        }
+    }
+
+    /// Entry of `aggregate` on `topic`, a token transfer in `block`.
+    fn event(topic: &str, aggregate: &str, block: u64) -> OutboxEvent {
+        let transfer = GhostnetEvent::Transfer(TransferEvent {
+            meta: meta(block),
+            from: Address::ZERO,
+            to: Address::ZERO,
+            value: U256::from(block),
+        });
+        OutboxEvent {
+            aggregate: aggregate.into(),
+            ..OutboxEvent::new(topic, &transfer).unwrap()
+        }
+    }
+
+    fn settings(batch_size: u32) -> OutboxSettings {
+        OutboxSettings {
+            batch_size,
+            retry_backoff_ms: 1000,
+            max_retry_backoff_ms: 4000,
+            ..OutboxSettings::default()
+        }
+    }
+
+    fn relay(
+        outbox: &Arc<MemoryOutbox>,
+        publisher: &Arc<RecordingPublisher>,
+        batch_size: u32,
+    ) -> OutboxRelay<MemoryOutbox, RecordingPublisher> {
+        OutboxRelay::new(
+            Arc::clone(outbox),
+            Arc::clone(publisher),
+            &settings(batch_size),
+        )
+    }
+
+    #[tokio::test]
+    async fn crash_after_commit_delivers_exactly_the_committed_events() {
+        let outbox = Arc::new(MemoryOutbox::default());
+        let publisher = Arc::new(RecordingPublisher::default());
+
+        // Three transactions commit; a fourth rolls back and never reaches the outbox
+        outbox.commit(&[event("positions", "position:a", 1)]);
+        outbox.commit(&[
+            event("positions", "position:b", 2),
+            event("market", "round:1", 2),
+        ]);
+        outbox.commit(&[event("positions", "position:a", 3)]);
+
+        // The process crashes after publishing part of the outbox
+        let now = Utc::now();
+        assert_eq!(
+            relay(&outbox, &publisher, 2).relay_once(now).await.unwrap(),
+            2
+        );
+        assert_eq!(publisher.delivered_ids(), [1, 2]);
+
+        // A new relay picks up where the old one stopped
+        let restarted = relay(&outbox, &publisher, 10);
+        assert_eq!(restarted.relay_once(now).await.unwrap(), 2);
+        assert_eq!(restarted.relay_once(now).await.unwrap(), 0);
+
+        assert_eq!(publisher.delivered_ids(), [1, 2, 3, 4]);
+        assert_eq!(outbox.outbox_depth().await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn crash_before_marking_redelivers_the_same_ids() {
+        let outbox = Arc::new(MemoryOutbox::default());
+        let publisher = Arc::new(RecordingPublisher::default());
+        outbox.commit(&[
+            event("positions", "position:a", 1),
+            event("positions", "position:a", 2),
+        ]);
+        outbox.fail_marks.store(1, Ordering::SeqCst);
+
+        let now = Utc::now();
+        assert!(
+            relay(&outbox, &publisher, 10)
+                .relay_once(now)
+                .await
+                .is_err()
+        );
+        relay(&outbox, &publisher, 10)
+            .relay_once(now)
+            .await
+            .unwrap();
+
+        // Published twice under the same IDs, so consumers deduplicate to
+        // exactly the committed events in order
+        assert_eq!(publisher.delivered_ids(), [1, 2, 1, 2]);
+        let mut seen = HashSet::new();
+        let unique: Vec<u128> = publisher
+            .delivered_ids()
+            .into_iter()
+            .filter(|id| seen.insert(*id))
+            .collect();
+        assert_eq!(unique, [1, 2]);
+        assert_eq!(outbox.outbox_depth().await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn failed_publish_holds_back_only_its_aggregate() {
+        let outbox = Arc::new(MemoryOutbox::default());
+        let publisher = Arc::new(RecordingPublisher::default());
+        outbox.commit(&[
+            event("positions", "position:a", 1),
+            event("market", "round:1", 1),
+            event("positions", "position:a", 2),
+            event("market", "round:1", 2),
+        ]);
+        publisher.fail("positions", 1);
+        let relay = relay(&outbox, &publisher, 10);
+
+        let now = Utc::now();
+        relay.relay_once(now).await.unwrap();
+        assert_eq!(publisher.delivered_ids(), [2, 4]);
+
+        // The failed entry waits for its backoff, and the later entry with it
+        let retry = now + chrono::Duration::seconds(1);
+        relay
+            .relay_once(retry - chrono::Duration::milliseconds(1))
+            .await
+            .unwrap();
+        assert_eq!(publisher.delivered_ids(), [2, 4]);
+
+        relay.relay_once(retry).await.unwrap();
+        assert_eq!(publisher.delivered_ids(), [2, 4, 1, 3]);
+    }
+
+    #[tokio::test]
+    async fn publish_order_within_aggregate_survives_interleaved_topics() {
+        let outbox = Arc::new(MemoryOutbox::default());
+        let publisher = Arc::new(RecordingPublisher::default());
+        outbox.commit(&[
+            event("positions", "position:a", 1),
+            event("scans", "scan:1", 1),
+            event("positions", "position:a", 2),
+        ]);
+
+        relay(&outbox, &publisher, 10)
+            .relay_once(Utc::now())
+            .await
+            .unwrap();
+
+        let delivered = publisher.delivered.lock().clone();
+        let expected = [("positions", 1), ("scans", 2), ("positions", 3)];
+        assert_eq!(delivered.len(), expected.len());
+        for ((topic, id), (expected_topic, expected_id)) in delivered.iter().zip(expected) {
+            assert_eq!((topic.as_str(), *id), (expected_topic, expected_id));
+        }
+    }
+
+    #[tokio::test]
+    async fn entries_are_published_in_every_schema_version() {
+        let outbox = Arc::new(MemoryOutbox::default());
+        let publisher = Arc::new(RecordingPublisher::default());
+        let jacked_in = GhostnetEvent::JackedIn(JackedInEvent {
+            meta: meta(1),
+            user: Address::ZERO,
+            amount: U256::from(1),
+            level: Level::Vault,
+            new_total: U256::from(1),
+        });
+        outbox.commit(&[OutboxEvent::new("positions", &jacked_in).unwrap()]);
+
+        relay(&outbox, &publisher, 10)
+            .relay_once(Utc::now())
+            .await
+            .unwrap();
+        let ids = publisher.delivered_ids();
+        assert_eq!(ids.len(), 2);
+        assert_eq!(ids[0], 1);
+        assert_ne!(ids[1], 1);
+
+        // After the deprecation window only version 2 is left
+        outbox.commit(&[OutboxEvent::new("positions", &jacked_in).unwrap()]);
+        let schemas = WireSchemas::new(&SchemaSettings {
+            versions: HashMap::from([("positions".to_string(), vec![2])]),
+        });
+        relay(&outbox, &publisher, 10)
+            .with_schemas(Arc::new(schemas))
+            .relay_once(Utc::now())
+            .await
+            .unwrap();
+        assert_eq!(publisher.delivered_ids().len(), 3);
+        assert_eq!(outbox.outbox_depth().await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn backoff_doubles_and_caps() {
+        let relay = relay(
+            &Arc::new(MemoryOutbox::default()),
+            &Arc::new(RecordingPublisher::default()),
+            10,
+        );
+        assert_eq!(relay.backoff(0), Duration::from_secs(1));
+        assert_eq!(relay.backoff(1), Duration::from_secs(2));
+        assert_eq!(relay.backoff(5), Duration::from_secs(4));
+        assert_eq!(relay.backoff(u32::MAX), Duration::from_secs(4));
+    }
+
+    #[tokio::test]
+    async fn purge_deletes_published_entries_past_retention() {
+        let outbox = Arc::new(MemoryOutbox::default());
+        let publisher = Arc::new(RecordingPublisher::default());
+        outbox.commit(&[event("positions", "position:a", 1)]);
+        let relay = relay(&outbox, &publisher, 10);
+
+        let now = Utc::now();
+        relay.relay_once(now).await.unwrap();
+        outbox.commit(&[event("positions", "position:a", 2)]);
+
+        assert_eq!(relay.purge(now).await.unwrap(), 0);
+        let later = now + chrono::Duration::hours(25);
+        assert_eq!(relay.purge(later).await.unwrap(), 1);
+        // Unpublished entries are kept however old
+        assert_eq!(outbox.outbox_depth().await.unwrap(), 1);
+        assert_eq!(outbox.rows.lock().len(), 1);
+    }
+}
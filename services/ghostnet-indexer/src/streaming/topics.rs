@@ -25,6 +25,9 @@ pub enum Topic {
     Token,
     /// Fee events: TollCollected, BuybackExecuted, OperationsWithdrawn
     Fees,
+    /// Events derived by the indexer rather than emitted on-chain:
+    /// RoundClosingSoon, RoundAwaitingResolution, BoostExpired
+    Derived,
 }
 
 impl Topic {
@@ -39,9 +42,16 @@ impl Topic {
             Self::System => "system",
             Self::Token => "token",
             Self::Fees => "fees",
+            Self::Derived => "derived",
         }
     }
 
+    /// Topic named `name`, as by [`Self::as_str`].
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::all().iter().copied().find(|topic| topic.as_str() == name)
+    }
+
     /// Get all topics for stream initialization.
     #[must_use]
     pub const fn all() -> &'static [Self] {
@@ -53,10 +63,13 @@ impl Topic {
             Self::System,
             Self::Token,
             Self::Fees,
+            Self::Derived,
         ]
     }
 
     /// Determine the appropriate topic for an event.
+    ///
+    /// On-chain events never go to [`Self::Derived`].
     #[must_use]
     pub const fn for_event(event: &GhostnetEvent) -> Self {
         match event {
@@ -165,11 +178,19 @@ mod tests {
         }
     }
 
+    #[test]
+    fn topics_are_found_by_name() {
+        for topic in Topic::all() {
+            assert_eq!(Topic::from_name(topic.as_str()), Some(*topic));
+        }
+        assert_eq!(Topic::from_name("Positions"), None);
+    }
+
     #[test]
     fn all_topics_covered() {
         // Ensure we have all expected topics
         let topics = Topic::all();
-        assert_eq!(topics.len(), 7);
+        assert_eq!(topics.len(), 8);
     }
 
     #[test]
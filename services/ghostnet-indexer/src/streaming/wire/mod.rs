@@ -0,0 +1,696 @@
+//! Wire schemas of the published events.
+//!
+//! What the indexer publishes is defined here, apart from the internal event
+//! and entity types, so those can change without breaking consumers. Every
+//! message is an [`Envelope`] around the DTO of its topic:
+//!
+//! ```json
+//! {
+//!   "schema_version": 1,
+//!   "topic": "positions",
+//!   "sequence": 42,
+//!   "timestamp": "2026-01-01T00:00:00Z",
+//!   "event": {"type": "JackedIn", "block": {...}, "user": "0x...", ...}
+//! }
+//! ```
+//!
+//! `sequence` is null until the [`EventLog`](super::EventLog) assigns one;
+//! events published through the buffered path keep it null.
+//!
+//! | Topic | Schemas |
+//! |-------|---------|
+//! | `positions` | [`PositionEventV1`], [`PositionEventV2`] |
+//! | `scans` | [`ScanEventV1`] |
+//! | `deaths` | [`DeathEventV1`] |
+//! | `market` | [`MarketEventV1`] |
+//! | `system` | [`SystemEventV1`] |
+//! | `token` | [`TokenEventV1`] |
+//! | `fees` | [`FeeEventV1`] |
+//! | `derived` | [`DerivedEventV1`] |
+//!
+//! # Versioning
+//!
+//! A published schema never changes; a change adds the DTO of the next
+//! version. While consumers move over, both versions are published: each
+//! event goes out once per version listed for its topic in
+//! `schemas.versions`, as messages of the same topic told apart by
+//! `schema_version`. Listing only the new version ends the deprecation
+//! window. WebSocket clients pick the version when subscribing.
+//!
+//! The golden files in `tests/golden/wire` pin the serialized form of every
+//! schema; a test failing on them means the wire format changed.
+
+mod v1;
+mod v2;
+
+use std::collections::HashMap;
+
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::Topic;
+use crate::config::SchemaSettings;
+use crate::error::{InfraError, Result};
+use crate::ports::IdentifiedMessage;
+use crate::types::events::{DerivedEvent, GhostnetEvent};
+
+pub use v1::{
+    BlockRefV1, DeathEventV1, DerivedEventV1, FeeEventV1, MarketEventV1, PositionEventV1,
+    ScanEventV1, SystemEventV1, TokenEventV1,
+};
+pub use v2::PositionEventV2;
+
+/// Schema versions the indexer has for `topic`, oldest first.
+#[must_use]
+pub const fn schema_versions(topic: Topic) -> &'static [u32] {
+    match topic {
+        Topic::Positions => &[1, 2],
+        _ => &[1],
+    }
+}
+
+/// Message ID of the `schema_version` encoding of the event with ID `id`.
+///
+/// Version 1 keeps the event's ID, so its messages are recognized as
+/// redeliveries of those published before the wire schemas existed.
+#[must_use]
+pub fn message_id(id: u128, schema_version: u32) -> u128 {
+    id ^ (u128::from(schema_version.saturating_sub(1)) << 96)
+}
+
+/// `payload` with its envelope's `sequence` set, or `None` if it is not an
+/// envelope.
+#[must_use]
+pub fn with_sequence(payload: &[u8], sequence: u64) -> Option<Vec<u8>> {
+    let mut envelope: Envelope<serde_json::Value> = serde_json::from_slice(payload).ok()?;
+    envelope.sequence = Some(sequence);
+    serde_json::to_vec(&envelope).ok()
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// ENVELOPE
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// A published event with its schema version and position.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Envelope<E> {
+    /// Version of the schema of `event`.
+    pub schema_version: u32,
+    /// Topic the event is published to.
+    pub topic: String,
+    /// Position of the event within its topic, once sequenced.
+    pub sequence: Option<u64>,
+    /// When the event happened: the block timestamp, or when the indexer
+    /// derived it.
+    pub timestamp: DateTime<Utc>,
+    /// The event.
+    pub event: E,
+}
+
+/// An event encoded in one schema version.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WireMessage {
+    /// Version of the schema the payload follows.
+    pub schema_version: u32,
+    /// Serialized [`Envelope`].
+    pub payload: Bytes,
+}
+
+impl WireMessage {
+    /// Message of the event with ID `id` in this encoding, see
+    /// [`message_id`].
+    #[must_use]
+    pub fn identified(self, id: u128) -> IdentifiedMessage {
+        IdentifiedMessage {
+            id: message_id(id, self.schema_version),
+            payload: self.payload,
+            sequence: None,
+        }
+    }
+}
+
+/// A schema version that is not published for a topic.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("schema version {version} is not published for topic {topic} (published: {published:?})")]
+pub struct UnsupportedSchema {
+    /// Topic asked for.
+    pub topic: Topic,
+    /// Version asked for.
+    pub version: u32,
+    /// Versions published for the topic.
+    pub published: Vec<u32>,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// WIRE SCHEMAS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Schema versions published per topic, and the encoding into them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WireSchemas {
+    /// Versions published per topic, oldest first.
+    versions: HashMap<Topic, Vec<u32>>,
+}
+
+impl Default for WireSchemas {
+    /// Every version the indexer has.
+    fn default() -> Self {
+        Self::new(&SchemaSettings::default())
+    }
+}
+
+impl WireSchemas {
+    /// Publish the versions configured in `settings`.
+    ///
+    /// Topics not configured publish every version the indexer has.
+    /// Versions the indexer does not have are ignored; settings validation
+    /// reports them.
+    #[must_use]
+    pub fn new(settings: &SchemaSettings) -> Self {
+        let versions = Topic::all()
+            .iter()
+            .map(|&topic| {
+                let known = schema_versions(topic);
+                let mut versions: Vec<u32> = settings
+                    .versions
+                    .get(topic.as_str())
+                    .map_or(known, Vec::as_slice)
+                    .iter()
+                    .copied()
+                    .filter(|version| known.contains(version))
+                    .collect();
+                versions.sort_unstable();
+                versions.dedup();
+                if versions.is_empty() {
+                    versions = known.to_vec();
+                }
+                (topic, versions)
+            })
+            .collect();
+        Self { versions }
+    }
+
+    /// Versions published for `topic`, oldest first.
+    #[must_use]
+    pub fn published(&self, topic: Topic) -> &[u32] {
+        self.versions.get(&topic).map_or(&[], Vec::as_slice)
+    }
+
+    /// Version delivered to a client of `topic` asking for `requested`.
+    ///
+    /// Without a request, the oldest version published, which clients
+    /// predating the newer ones were built against.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the version asked for is not published.
+    pub fn negotiate(
+        &self,
+        topic: Topic,
+        requested: Option<u32>,
+    ) -> std::result::Result<u32, UnsupportedSchema> {
+        let published = self.published(topic);
+        let version = requested
+            .or_else(|| published.first().copied())
+            .unwrap_or(1);
+        if published.contains(&version) {
+            Ok(version)
+        } else {
+            Err(UnsupportedSchema {
+                topic,
+                version,
+                published: published.to_vec(),
+            })
+        }
+    }
+
+    /// Encode `event` in every version published for its topic.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the event cannot be serialized.
+    pub fn encode(&self, event: &GhostnetEvent) -> Result<Vec<WireMessage>> {
+        let frame = Frame {
+            topic: Topic::for_event(event),
+            timestamp: event.metadata().timestamp,
+            name: event.type_name(),
+        };
+        self.published(frame.topic)
+            .iter()
+            .map(|&version| match (frame.topic, version) {
+                (Topic::Positions, 2) => frame.wrap(version, PositionEventV2::from_event(event)),
+                (Topic::Positions, _) => frame.wrap(version, PositionEventV1::from_event(event)),
+                (Topic::Scans, _) => frame.wrap(version, ScanEventV1::from_event(event)),
+                (Topic::Deaths, _) => frame.wrap(version, DeathEventV1::from_event(event)),
+                (Topic::Market, _) => frame.wrap(version, MarketEventV1::from_event(event)),
+                (Topic::System, _) => frame.wrap(version, SystemEventV1::from_event(event)),
+                (Topic::Token, _) => frame.wrap(version, TokenEventV1::from_event(event)),
+                (Topic::Fees, _) => frame.wrap(version, FeeEventV1::from_event(event)),
+                // On-chain events never go to the derived topic
+                (Topic::Derived, _) => frame.wrap(version, None::<DerivedEventV1>),
+            })
+            .collect()
+    }
+
+    /// Encode the derived `event` in every version published for the
+    /// `derived` topic.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the event cannot be serialized.
+    pub fn encode_derived(&self, event: &DerivedEvent) -> Result<Vec<WireMessage>> {
+        let frame = Frame {
+            topic: Topic::Derived,
+            timestamp: match event {
+                DerivedEvent::RoundClosingSoon(e) => e.derived_at,
+                DerivedEvent::RoundAwaitingResolution(e) => e.derived_at,
+                DerivedEvent::BoostExpired(e) => e.derived_at,
+            },
+            name: event.event_name(),
+        };
+        self.published(frame.topic)
+            .iter()
+            .map(|&version| frame.wrap(version, Some(DerivedEventV1::from(event))))
+            .collect()
+    }
+}
+
+/// What the envelopes of one event share.
+struct Frame {
+    topic: Topic,
+    timestamp: DateTime<Utc>,
+    /// Name of the event, for errors.
+    name: &'static str,
+}
+
+impl Frame {
+    /// Serialize the envelope of `event`, the event's DTO in `schema_version`.
+    fn wrap<E: Serialize>(&self, schema_version: u32, event: Option<E>) -> Result<WireMessage> {
+        let (topic, name) = (self.topic, self.name);
+        let event = event.ok_or_else(|| {
+            InfraError::Streaming(format!("No {topic} v{schema_version} schema for {name}"))
+        })?;
+        let envelope = Envelope {
+            schema_version,
+            topic: topic.as_str().to_string(),
+            sequence: None,
+            timestamp: self.timestamp,
+            event,
+        };
+        let payload = serde_json::to_vec(&envelope)
+            .map_err(|e| InfraError::Streaming(format!("Failed to serialize {name}: {e}")))?;
+        Ok(WireMessage {
+            schema_version,
+            payload: payload.into(),
+        })
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use alloy::primitives::{Address, B256, U256};
+    use chrono::TimeZone;
+    use serde::de::DeserializeOwned;
+    use serde_json::Value;
+
+    use super::*;
+    use crate::types::enums::{BoostType, Level, RoundType};
+    use crate::types::events::*;
+
+    fn at(hour: u32, min: u32, sec: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 1, 1, hour, min, sec).unwrap()
+    }
+
+    fn meta() -> EventMetadata {
+        EventMetadata {
+            block_number: 100,
+            block_hash: B256::repeat_byte(0x11),
+            tx_hash: B256::repeat_byte(0x22),
+            tx_index: 3,
+            log_index: 4,
+            timestamp: at(0, 0, 0),
+            contract: Address::repeat_byte(0x33),
+            chain_id: 6343,
+            tx_function: Some("GhostCore.jackIn".into()),
+            tx_from: None,
+        }
+    }
+
+    /// `n` thousandths of a token, in wei.
+    fn milli(n: u64) -> U256 {
+        U256::from(n) * U256::from(1_000_000_000_000_000_u64)
+    }
+
+    fn user() -> Address {
+        Address::repeat_byte(0xaa)
+    }
+
+    /// One event of every on-chain type.
+    #[allow(clippy::too_many_lines)]
+    fn events() -> Vec<GhostnetEvent> {
+        let meta = meta();
+        vec![
+            GhostnetEvent::JackedIn(JackedInEvent {
+                meta: meta.clone(),
+                user: user(),
+                amount: milli(1500),
+                level: Level::Darknet,
+                new_total: milli(2500),
+            }),
+            GhostnetEvent::StakeAdded(StakeAddedEvent {
+                meta: meta.clone(),
+                user: user(),
+                amount: milli(1000),
+                new_total: milli(2500),
+            }),
+            GhostnetEvent::Extracted(ExtractedEvent {
+                meta: meta.clone(),
+                user: user(),
+                amount: milli(2500),
+                rewards: milli(250),
+            }),
+            GhostnetEvent::PositionCulled(PositionCulledEvent {
+                meta: meta.clone(),
+                victim: user(),
+                penalty_amount: milli(500),
+                returned_amount: milli(2000),
+                new_entrant: Address::repeat_byte(0xbb),
+            }),
+            GhostnetEvent::BoostApplied(BoostAppliedEvent {
+                meta: meta.clone(),
+                user: user(),
+                boost_type: BoostType::DeathReduction,
+                value_bps: 1500,
+                expiry: 1_767_229_200,
+            }),
+            GhostnetEvent::ScanExecuted(ScanExecutedEvent {
+                meta: meta.clone(),
+                level: Level::Darknet,
+                scan_id: U256::from(42),
+                seed: U256::from(123_456_789),
+                executed_at: 1_767_225_600,
+            }),
+            GhostnetEvent::DeathsSubmitted(DeathsSubmittedEvent {
+                meta: meta.clone(),
+                level: Level::Darknet,
+                scan_id: U256::from(42),
+                count: U256::from(3),
+                total_dead: milli(4500),
+                submitter: Address::repeat_byte(0xcc),
+            }),
+            GhostnetEvent::ScanFinalized(ScanFinalizedEvent {
+                meta: meta.clone(),
+                level: Level::Darknet,
+                scan_id: U256::from(42),
+                death_count: U256::from(3),
+                total_dead: milli(4500),
+                finalized_at: 1_767_225_660,
+            }),
+            GhostnetEvent::DeathsProcessed(DeathsProcessedEvent {
+                meta: meta.clone(),
+                level: Level::Darknet,
+                count: U256::from(3),
+                total_dead: milli(4500),
+                burned: milli(1350),
+                distributed: milli(3150),
+            }),
+            GhostnetEvent::SurvivorsUpdated(SurvivorsUpdatedEvent {
+                meta: meta.clone(),
+                level: Level::Darknet,
+                count: U256::from(17),
+            }),
+            GhostnetEvent::CascadeDistributed(CascadeDistributedEvent {
+                meta: meta.clone(),
+                source_level: Level::Darknet,
+                same_level_amount: milli(1350),
+                upstream_amount: milli(1350),
+                burn_amount: milli(1350),
+                protocol_amount: milli(450),
+            }),
+            GhostnetEvent::RoundCreated(RoundCreatedEvent {
+                meta: meta.clone(),
+                round_id: U256::from(7),
+                round_type: RoundType::DeathCount,
+                target_level: Level::Darknet,
+                line: U256::from(5),
+                deadline: 1_767_232_800,
+            }),
+            GhostnetEvent::BetPlaced(BetPlacedEvent {
+                meta: meta.clone(),
+                round_id: U256::from(7),
+                user: user(),
+                is_over: true,
+                amount: milli(1000),
+            }),
+            GhostnetEvent::RoundResolved(RoundResolvedEvent {
+                meta: meta.clone(),
+                round_id: U256::from(7),
+                outcome: false,
+                total_pot: milli(10_000),
+                burned: milli(500),
+            }),
+            GhostnetEvent::WinningsClaimed(WinningsClaimedEvent {
+                meta: meta.clone(),
+                round_id: U256::from(7),
+                user: user(),
+                amount: milli(1900),
+            }),
+            GhostnetEvent::SystemResetTriggered(SystemResetTriggeredEvent {
+                meta: meta.clone(),
+                total_penalty: milli(25_000),
+                jackpot_winner: user(),
+                jackpot_amount: milli(12_500),
+            }),
+            GhostnetEvent::EmissionsAdded(EmissionsAddedEvent {
+                meta: meta.clone(),
+                level: Level::Vault,
+                amount: milli(100_000),
+            }),
+            GhostnetEvent::EmissionsDistributed(EmissionsDistributedEvent {
+                meta: meta.clone(),
+                total_amount: milli(500_000),
+                timestamp: 1_767_225_600,
+            }),
+            GhostnetEvent::WeightsUpdated(WeightsUpdatedEvent {
+                meta: meta.clone(),
+                new_weights: [500, 1000, 2000, 3000, 3500],
+            }),
+            GhostnetEvent::TokensClaimed(TokensClaimedEvent {
+                meta: meta.clone(),
+                beneficiary: Address::repeat_byte(0xdd),
+                amount: milli(1_000_000),
+            }),
+            GhostnetEvent::Transfer(TransferEvent {
+                meta: meta.clone(),
+                from: user(),
+                to: Address::repeat_byte(0xbb),
+                value: milli(1000),
+            }),
+            GhostnetEvent::TaxBurned(TaxBurnedEvent {
+                meta: meta.clone(),
+                from: user(),
+                amount: milli(90),
+            }),
+            GhostnetEvent::TaxCollected(TaxCollectedEvent {
+                meta: meta.clone(),
+                from: user(),
+                amount: milli(10),
+            }),
+            GhostnetEvent::TaxExclusionSet(TaxExclusionSetEvent {
+                meta: meta.clone(),
+                account: Address::repeat_byte(0xbb),
+                excluded: true,
+            }),
+            GhostnetEvent::TollCollected(TollCollectedEvent {
+                meta: meta.clone(),
+                from: user(),
+                amount: milli(1),
+                reason: B256::repeat_byte(0x44),
+            }),
+            GhostnetEvent::BuybackExecuted(BuybackExecutedEvent {
+                meta: meta.clone(),
+                eth_spent: milli(2000),
+                data_received: milli(5_000_000),
+                data_burned: milli(5_000_000),
+            }),
+            GhostnetEvent::OperationsWithdrawn(OperationsWithdrawnEvent {
+                meta,
+                to: Address::repeat_byte(0xee),
+                amount: milli(1000),
+            }),
+        ]
+    }
+
+    fn derived_events() -> Vec<DerivedEvent> {
+        vec![
+            DerivedEvent::RoundClosingSoon(RoundClosingSoonEvent {
+                round_id: U256::from(7),
+                threshold_secs: 600,
+                seconds_remaining: 540,
+                deadline: at(2, 0, 0),
+                derived_at: at(1, 51, 0),
+            }),
+            DerivedEvent::RoundAwaitingResolution(RoundAwaitingResolutionEvent {
+                round_id: U256::from(7),
+                deadline: at(2, 0, 0),
+                seconds_overdue: 30,
+                derived_at: at(2, 0, 30),
+            }),
+            DerivedEvent::BoostExpired(BoostExpiredEvent {
+                boost_id: uuid::Uuid::from_u128(0x0195_0000_0000_7000_8000_0000_0000_0001),
+                user: user(),
+                boost_type: BoostType::DeathReduction,
+                value_bps: 1500,
+                expiry: at(1, 0, 0),
+                ended_at: at(0, 40, 0),
+                position_closed: true,
+                derived_at: at(0, 40, 5),
+            }),
+        ]
+    }
+
+    fn golden_path(topic: Topic, version: u32) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/golden/wire")
+            .join(format!("{topic}.v{version}.json"))
+    }
+
+    fn read_golden(topic: Topic, version: u32) -> Value {
+        let json = std::fs::read_to_string(golden_path(topic, version)).unwrap();
+        serde_json::from_str(&json).unwrap()
+    }
+
+    /// Whether the envelopes of the golden file read back into `E` as they
+    /// were.
+    fn round_trips<E: DeserializeOwned + Serialize>(topic: Topic, version: u32) -> bool {
+        let golden = read_golden(topic, version);
+        let envelopes: Vec<Envelope<E>> = serde_json::from_value(golden.clone()).unwrap();
+        serde_json::to_value(&envelopes).unwrap() == golden
+    }
+
+    fn positions(versions: &[u32]) -> WireSchemas {
+        WireSchemas::new(&SchemaSettings {
+            versions: HashMap::from([("positions".to_string(), versions.to_vec())]),
+        })
+    }
+
+    #[test]
+    fn every_schema_matches_its_golden_file() {
+        let schemas = WireSchemas::default();
+
+        for &topic in Topic::all() {
+            for &version in schema_versions(topic) {
+                let messages = if topic == Topic::Derived {
+                    derived_events()
+                        .iter()
+                        .flat_map(|event| schemas.encode_derived(event).unwrap())
+                        .collect::<Vec<_>>()
+                } else {
+                    events()
+                        .iter()
+                        .filter(|event| Topic::for_event(event) == topic)
+                        .flat_map(|event| schemas.encode(event).unwrap())
+                        .collect()
+                };
+                let actual: Value = messages
+                    .iter()
+                    .filter(|message| message.schema_version == version)
+                    .map(|message| serde_json::from_slice::<Value>(&message.payload).unwrap())
+                    .collect();
+
+                // Regenerate with UPDATE_GOLDEN=1 after adding a schema; a
+                // published one must not change
+                if std::env::var_os("UPDATE_GOLDEN").is_some() {
+                    let json = serde_json::to_string_pretty(&actual).unwrap() + "\n";
+                    std::fs::write(golden_path(topic, version), json).unwrap();
+                }
+                assert_eq!(actual, read_golden(topic, version), "{topic} v{version}");
+            }
+        }
+    }
+
+    #[test]
+    fn golden_files_read_back_into_their_schemas() {
+        assert!(round_trips::<PositionEventV1>(Topic::Positions, 1));
+        assert!(round_trips::<PositionEventV2>(Topic::Positions, 2));
+        assert!(round_trips::<ScanEventV1>(Topic::Scans, 1));
+        assert!(round_trips::<DeathEventV1>(Topic::Deaths, 1));
+        assert!(round_trips::<MarketEventV1>(Topic::Market, 1));
+        assert!(round_trips::<SystemEventV1>(Topic::System, 1));
+        assert!(round_trips::<TokenEventV1>(Topic::Token, 1));
+        assert!(round_trips::<FeeEventV1>(Topic::Fees, 1));
+        assert!(round_trips::<DerivedEventV1>(Topic::Derived, 1));
+    }
+
+    #[test]
+    fn both_versions_are_published_during_the_deprecation_window() {
+        let schemas = positions(&[1, 2]);
+        let jacked_in = &events()[0];
+
+        let messages = schemas.encode(jacked_in).unwrap();
+        let versions: Vec<u32> = messages.iter().map(|m| m.schema_version).collect();
+        assert_eq!(versions, [1, 2]);
+
+        let amounts: Vec<Value> = messages
+            .iter()
+            .map(|m| {
+                serde_json::from_slice::<Value>(&m.payload).unwrap()["event"]["amount"].clone()
+            })
+            .collect();
+        assert_eq!(amounts, ["1500000000000000000", "1.5"]);
+
+        // Both encodings are kept by deduplication, version 1 under the
+        // event's own ID
+        let ids: Vec<u128> = messages.into_iter().map(|m| m.identified(7).id).collect();
+        assert_eq!(ids[0], 7);
+        assert_ne!(ids[0], ids[1]);
+
+        assert_eq!(schemas.negotiate(Topic::Positions, None), Ok(1));
+        assert_eq!(schemas.negotiate(Topic::Positions, Some(2)), Ok(2));
+    }
+
+    #[test]
+    fn only_the_new_version_is_published_after_the_window() {
+        let schemas = positions(&[2]);
+
+        let messages = schemas.encode(&events()[0]).unwrap();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].schema_version, 2);
+
+        assert_eq!(schemas.negotiate(Topic::Positions, None), Ok(2));
+        let err = schemas.negotiate(Topic::Positions, Some(1)).unwrap_err();
+        assert_eq!(err.published, [2]);
+        // Other topics are unaffected
+        assert_eq!(schemas.negotiate(Topic::Token, None), Ok(1));
+    }
+
+    #[test]
+    fn unsupported_versions_in_settings_are_ignored() {
+        assert_eq!(positions(&[2, 9, 2]).published(Topic::Positions), [2]);
+        assert_eq!(positions(&[9]).published(Topic::Positions), [1, 2]);
+    }
+
+    #[test]
+    fn sequence_is_stamped_into_envelopes_only() {
+        let message = WireSchemas::default()
+            .encode(&events()[20])
+            .unwrap()
+            .remove(0);
+
+        let stamped = with_sequence(&message.payload, 42).unwrap();
+        let envelope: Envelope<Value> = serde_json::from_slice(&stamped).unwrap();
+        assert_eq!(envelope.sequence, Some(42));
+        assert_eq!(envelope.topic, "token");
+
+        assert_eq!(with_sequence(br#"{"id":1}"#, 42), None);
+    }
+}
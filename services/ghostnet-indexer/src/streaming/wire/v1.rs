@@ -0,0 +1,741 @@
+//! Version 1 of the wire schemas.
+//!
+//! Mirrors the contract events without their [`EventMetadata`]: where an
+//! event was emitted is in its [`BlockRefV1`], when in the envelope. Integers
+//! that are `uint256` on-chain (amounts in wei, IDs, counts) are decimal
+//! strings, addresses and hashes lowercase `0x`-prefixed hex.
+
+use alloy::primitives::{B256, U256};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::types::enums::{BoostType, Level, RoundType};
+use crate::types::events::{DerivedEvent, EventMetadata, GhostnetEvent};
+use crate::types::primitives::EthAddress;
+
+/// Decimal string of a `uint256`.
+fn decimal(value: U256) -> String {
+    value.to_string()
+}
+
+/// Lowercase `0x`-prefixed hex of a hash.
+fn hex(hash: &B256) -> String {
+    format!("{hash:#x}")
+}
+
+/// Where an on-chain event was emitted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockRefV1 {
+    /// Block number.
+    pub number: u64,
+    /// Block hash.
+    pub hash: String,
+    /// Hash of the transaction that emitted the event.
+    pub tx_hash: String,
+    /// Index of the log within the transaction.
+    pub log_index: u64,
+}
+
+impl From<&EventMetadata> for BlockRefV1 {
+    fn from(meta: &EventMetadata) -> Self {
+        Self {
+            number: meta.block_number,
+            hash: hex(&meta.block_hash),
+            tx_hash: hex(&meta.tx_hash),
+            log_index: meta.log_index,
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// POSITIONS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Event of the `positions` topic, version 1.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum PositionEventV1 {
+    /// A user entered a position.
+    JackedIn {
+        /// Where the event was emitted.
+        block: BlockRefV1,
+        /// User's wallet address.
+        user: EthAddress,
+        /// Amount of DATA staked, in wei.
+        amount: String,
+        /// Risk level chosen.
+        level: Level,
+        /// Total staked after this action, in wei.
+        new_total: String,
+    },
+    /// A user added to an existing position.
+    StakeAdded {
+        /// Where the event was emitted.
+        block: BlockRefV1,
+        /// User's wallet address.
+        user: EthAddress,
+        /// Amount added, in wei.
+        amount: String,
+        /// New total stake, in wei.
+        new_total: String,
+    },
+    /// A user extracted their position.
+    Extracted {
+        /// Where the event was emitted.
+        block: BlockRefV1,
+        /// User's wallet address.
+        user: EthAddress,
+        /// Principal returned, in wei.
+        amount: String,
+        /// Rewards earned, in wei.
+        rewards: String,
+    },
+    /// A position was culled due to level capacity.
+    PositionCulled {
+        /// Where the event was emitted.
+        block: BlockRefV1,
+        /// User who was culled.
+        victim: EthAddress,
+        /// Penalty taken, in wei.
+        penalty_amount: String,
+        /// Amount returned to the victim, in wei.
+        returned_amount: String,
+        /// User who triggered the culling.
+        new_entrant: EthAddress,
+    },
+    /// A boost was applied to a user.
+    BoostApplied {
+        /// Where the event was emitted.
+        block: BlockRefV1,
+        /// User receiving the boost.
+        user: EthAddress,
+        /// Type of boost.
+        boost_type: BoostType,
+        /// Boost value in basis points.
+        value_bps: u16,
+        /// Unix timestamp when the boost expires.
+        expiry: u64,
+    },
+}
+
+impl PositionEventV1 {
+    /// DTO of `event`, or `None` if it is not published to `positions`.
+    #[must_use]
+    pub fn from_event(event: &GhostnetEvent) -> Option<Self> {
+        Some(match event {
+            GhostnetEvent::JackedIn(e) => Self::JackedIn {
+                block: (&e.meta).into(),
+                user: e.user.into(),
+                amount: decimal(e.amount),
+                level: e.level,
+                new_total: decimal(e.new_total),
+            },
+            GhostnetEvent::StakeAdded(e) => Self::StakeAdded {
+                block: (&e.meta).into(),
+                user: e.user.into(),
+                amount: decimal(e.amount),
+                new_total: decimal(e.new_total),
+            },
+            GhostnetEvent::Extracted(e) => Self::Extracted {
+                block: (&e.meta).into(),
+                user: e.user.into(),
+                amount: decimal(e.amount),
+                rewards: decimal(e.rewards),
+            },
+            GhostnetEvent::PositionCulled(e) => Self::PositionCulled {
+                block: (&e.meta).into(),
+                victim: e.victim.into(),
+                penalty_amount: decimal(e.penalty_amount),
+                returned_amount: decimal(e.returned_amount),
+                new_entrant: e.new_entrant.into(),
+            },
+            GhostnetEvent::BoostApplied(e) => Self::BoostApplied {
+                block: (&e.meta).into(),
+                user: e.user.into(),
+                boost_type: e.boost_type,
+                value_bps: e.value_bps,
+                expiry: e.expiry,
+            },
+            _ => return None,
+        })
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// SCANS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Event of the `scans` topic, version 1.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum ScanEventV1 {
+    /// A scan was executed (phase 1).
+    ScanExecuted {
+        /// Where the event was emitted.
+        block: BlockRefV1,
+        /// Level being scanned.
+        level: Level,
+        /// Scan identifier.
+        scan_id: String,
+        /// Deterministic seed from prevrandao.
+        seed: String,
+        /// Unix timestamp of the execution.
+        executed_at: u64,
+    },
+    /// Deaths were submitted for a scan.
+    DeathsSubmitted {
+        /// Where the event was emitted.
+        block: BlockRefV1,
+        /// Level scanned.
+        level: Level,
+        /// Scan identifier.
+        scan_id: String,
+        /// Deaths in this batch.
+        count: String,
+        /// Total DATA of the dead positions, in wei.
+        total_dead: String,
+        /// Who submitted the batch.
+        submitter: EthAddress,
+    },
+    /// A scan was finalized (phase 2).
+    ScanFinalized {
+        /// Where the event was emitted.
+        block: BlockRefV1,
+        /// Level scanned.
+        level: Level,
+        /// Scan identifier.
+        scan_id: String,
+        /// Total deaths processed.
+        death_count: String,
+        /// Total DATA lost, in wei.
+        total_dead: String,
+        /// Unix timestamp of the finalization.
+        finalized_at: u64,
+    },
+}
+
+impl ScanEventV1 {
+    /// DTO of `event`, or `None` if it is not published to `scans`.
+    #[must_use]
+    pub fn from_event(event: &GhostnetEvent) -> Option<Self> {
+        Some(match event {
+            GhostnetEvent::ScanExecuted(e) => Self::ScanExecuted {
+                block: (&e.meta).into(),
+                level: e.level,
+                scan_id: decimal(e.scan_id),
+                seed: decimal(e.seed),
+                executed_at: e.executed_at,
+            },
+            GhostnetEvent::DeathsSubmitted(e) => Self::DeathsSubmitted {
+                block: (&e.meta).into(),
+                level: e.level,
+                scan_id: decimal(e.scan_id),
+                count: decimal(e.count),
+                total_dead: decimal(e.total_dead),
+                submitter: e.submitter.into(),
+            },
+            GhostnetEvent::ScanFinalized(e) => Self::ScanFinalized {
+                block: (&e.meta).into(),
+                level: e.level,
+                scan_id: decimal(e.scan_id),
+                death_count: decimal(e.death_count),
+                total_dead: decimal(e.total_dead),
+                finalized_at: e.finalized_at,
+            },
+            _ => return None,
+        })
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// DEATHS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Event of the `deaths` topic, version 1.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum DeathEventV1 {
+    /// Deaths were processed after a scan.
+    DeathsProcessed {
+        /// Where the event was emitted.
+        block: BlockRefV1,
+        /// Level scanned.
+        level: Level,
+        /// Deaths in this batch.
+        count: String,
+        /// Total DATA of the dead positions, in wei.
+        total_dead: String,
+        /// Amount burned, in wei.
+        burned: String,
+        /// Amount distributed to survivors, in wei.
+        distributed: String,
+    },
+    /// Survivor streaks were updated after a scan.
+    SurvivorsUpdated {
+        /// Where the event was emitted.
+        block: BlockRefV1,
+        /// Level scanned.
+        level: Level,
+        /// Number of survivors.
+        count: String,
+    },
+    /// Cascade rewards were distributed from deaths.
+    CascadeDistributed {
+        /// Where the event was emitted.
+        block: BlockRefV1,
+        /// Level where the deaths occurred.
+        source_level: Level,
+        /// Distributed to same-level survivors, in wei.
+        same_level_amount: String,
+        /// Distributed to safer levels, in wei.
+        upstream_amount: String,
+        /// Burned, in wei.
+        burn_amount: String,
+        /// Sent to the treasury, in wei.
+        protocol_amount: String,
+    },
+}
+
+impl DeathEventV1 {
+    /// DTO of `event`, or `None` if it is not published to `deaths`.
+    #[must_use]
+    pub fn from_event(event: &GhostnetEvent) -> Option<Self> {
+        Some(match event {
+            GhostnetEvent::DeathsProcessed(e) => Self::DeathsProcessed {
+                block: (&e.meta).into(),
+                level: e.level,
+                count: decimal(e.count),
+                total_dead: decimal(e.total_dead),
+                burned: decimal(e.burned),
+                distributed: decimal(e.distributed),
+            },
+            GhostnetEvent::SurvivorsUpdated(e) => Self::SurvivorsUpdated {
+                block: (&e.meta).into(),
+                level: e.level,
+                count: decimal(e.count),
+            },
+            GhostnetEvent::CascadeDistributed(e) => Self::CascadeDistributed {
+                block: (&e.meta).into(),
+                source_level: e.source_level,
+                same_level_amount: decimal(e.same_level_amount),
+                upstream_amount: decimal(e.upstream_amount),
+                burn_amount: decimal(e.burn_amount),
+                protocol_amount: decimal(e.protocol_amount),
+            },
+            _ => return None,
+        })
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// MARKET
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Event of the `market` topic, version 1.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum MarketEventV1 {
+    /// A betting round was created.
+    RoundCreated {
+        /// Where the event was emitted.
+        block: BlockRefV1,
+        /// Round identifier.
+        round_id: String,
+        /// Type of round.
+        round_type: RoundType,
+        /// Target level.
+        target_level: Level,
+        /// Over/under line.
+        line: String,
+        /// Unix timestamp when betting closes.
+        deadline: u64,
+    },
+    /// A bet was placed.
+    BetPlaced {
+        /// Where the event was emitted.
+        block: BlockRefV1,
+        /// Round identifier.
+        round_id: String,
+        /// Bettor's address.
+        user: EthAddress,
+        /// `true` for over, `false` for under.
+        is_over: bool,
+        /// Amount wagered, in wei.
+        amount: String,
+    },
+    /// A round was resolved.
+    RoundResolved {
+        /// Where the event was emitted.
+        block: BlockRefV1,
+        /// Round identifier.
+        round_id: String,
+        /// `true` if over won, `false` if under won.
+        outcome: bool,
+        /// Total pot, in wei.
+        total_pot: String,
+        /// Rake burned, in wei.
+        burned: String,
+    },
+    /// Winnings were claimed.
+    WinningsClaimed {
+        /// Where the event was emitted.
+        block: BlockRefV1,
+        /// Round identifier.
+        round_id: String,
+        /// Winner's address.
+        user: EthAddress,
+        /// Payout, in wei.
+        amount: String,
+    },
+}
+
+impl MarketEventV1 {
+    /// DTO of `event`, or `None` if it is not published to `market`.
+    #[must_use]
+    pub fn from_event(event: &GhostnetEvent) -> Option<Self> {
+        Some(match event {
+            GhostnetEvent::RoundCreated(e) => Self::RoundCreated {
+                block: (&e.meta).into(),
+                round_id: decimal(e.round_id),
+                round_type: e.round_type,
+                target_level: e.target_level,
+                line: decimal(e.line),
+                deadline: e.deadline,
+            },
+            GhostnetEvent::BetPlaced(e) => Self::BetPlaced {
+                block: (&e.meta).into(),
+                round_id: decimal(e.round_id),
+                user: e.user.into(),
+                is_over: e.is_over,
+                amount: decimal(e.amount),
+            },
+            GhostnetEvent::RoundResolved(e) => Self::RoundResolved {
+                block: (&e.meta).into(),
+                round_id: decimal(e.round_id),
+                outcome: e.outcome,
+                total_pot: decimal(e.total_pot),
+                burned: decimal(e.burned),
+            },
+            GhostnetEvent::WinningsClaimed(e) => Self::WinningsClaimed {
+                block: (&e.meta).into(),
+                round_id: decimal(e.round_id),
+                user: e.user.into(),
+                amount: decimal(e.amount),
+            },
+            _ => return None,
+        })
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// SYSTEM
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Event of the `system` topic, version 1.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum SystemEventV1 {
+    /// A system reset was triggered.
+    SystemResetTriggered {
+        /// Where the event was emitted.
+        block: BlockRefV1,
+        /// Total penalty taken from all positions, in wei.
+        total_penalty: String,
+        /// Last depositor, who wins the jackpot.
+        jackpot_winner: EthAddress,
+        /// Jackpot payout, in wei.
+        jackpot_amount: String,
+    },
+    /// Emissions were added to a level.
+    EmissionsAdded {
+        /// Where the event was emitted.
+        block: BlockRefV1,
+        /// Target level.
+        level: Level,
+        /// Amount of emissions, in wei.
+        amount: String,
+    },
+    /// Emissions were distributed across the levels.
+    EmissionsDistributed {
+        /// Where the event was emitted.
+        block: BlockRefV1,
+        /// Total DATA distributed, in wei.
+        total_amount: String,
+        /// Unix timestamp of the distribution.
+        timestamp: u64,
+    },
+    /// Level weights were updated.
+    WeightsUpdated {
+        /// Where the event was emitted.
+        block: BlockRefV1,
+        /// New weights of levels 1-5.
+        new_weights: [u16; 5],
+    },
+    /// A team member claimed vested tokens.
+    TokensClaimed {
+        /// Where the event was emitted.
+        block: BlockRefV1,
+        /// Team member's address.
+        beneficiary: EthAddress,
+        /// Amount claimed, in wei.
+        amount: String,
+    },
+}
+
+impl SystemEventV1 {
+    /// DTO of `event`, or `None` if it is not published to `system`.
+    #[must_use]
+    pub fn from_event(event: &GhostnetEvent) -> Option<Self> {
+        Some(match event {
+            GhostnetEvent::SystemResetTriggered(e) => Self::SystemResetTriggered {
+                block: (&e.meta).into(),
+                total_penalty: decimal(e.total_penalty),
+                jackpot_winner: e.jackpot_winner.into(),
+                jackpot_amount: decimal(e.jackpot_amount),
+            },
+            GhostnetEvent::EmissionsAdded(e) => Self::EmissionsAdded {
+                block: (&e.meta).into(),
+                level: e.level,
+                amount: decimal(e.amount),
+            },
+            GhostnetEvent::EmissionsDistributed(e) => Self::EmissionsDistributed {
+                block: (&e.meta).into(),
+                total_amount: decimal(e.total_amount),
+                timestamp: e.timestamp,
+            },
+            GhostnetEvent::WeightsUpdated(e) => Self::WeightsUpdated {
+                block: (&e.meta).into(),
+                new_weights: e.new_weights,
+            },
+            GhostnetEvent::TokensClaimed(e) => Self::TokensClaimed {
+                block: (&e.meta).into(),
+                beneficiary: e.beneficiary.into(),
+                amount: decimal(e.amount),
+            },
+            _ => return None,
+        })
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TOKEN
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Event of the `token` topic, version 1.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum TokenEventV1 {
+    /// DATA was transferred.
+    Transfer {
+        /// Where the event was emitted.
+        block: BlockRefV1,
+        /// Sender, the zero address for mints.
+        from: EthAddress,
+        /// Recipient.
+        to: EthAddress,
+        /// Amount transferred, in wei.
+        value: String,
+    },
+    /// Tax was burned.
+    TaxBurned {
+        /// Where the event was emitted.
+        block: BlockRefV1,
+        /// Sender of the taxed transfer.
+        from: EthAddress,
+        /// Amount burned, in wei.
+        amount: String,
+    },
+    /// Tax was sent to the treasury.
+    TaxCollected {
+        /// Where the event was emitted.
+        block: BlockRefV1,
+        /// Sender of the taxed transfer.
+        from: EthAddress,
+        /// Amount sent to the treasury, in wei.
+        amount: String,
+    },
+    /// An address's tax exclusion changed.
+    TaxExclusionSet {
+        /// Where the event was emitted.
+        block: BlockRefV1,
+        /// Affected address.
+        account: EthAddress,
+        /// Whether the address is now excluded.
+        excluded: bool,
+    },
+}
+
+impl TokenEventV1 {
+    /// DTO of `event`, or `None` if it is not published to `token`.
+    #[must_use]
+    pub fn from_event(event: &GhostnetEvent) -> Option<Self> {
+        Some(match event {
+            GhostnetEvent::Transfer(e) => Self::Transfer {
+                block: (&e.meta).into(),
+                from: e.from.into(),
+                to: e.to.into(),
+                value: decimal(e.value),
+            },
+            GhostnetEvent::TaxBurned(e) => Self::TaxBurned {
+                block: (&e.meta).into(),
+                from: e.from.into(),
+                amount: decimal(e.amount),
+            },
+            GhostnetEvent::TaxCollected(e) => Self::TaxCollected {
+                block: (&e.meta).into(),
+                from: e.from.into(),
+                amount: decimal(e.amount),
+            },
+            GhostnetEvent::TaxExclusionSet(e) => Self::TaxExclusionSet {
+                block: (&e.meta).into(),
+                account: e.account.into(),
+                excluded: e.excluded,
+            },
+            _ => return None,
+        })
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// FEES
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Event of the `fees` topic, version 1.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum FeeEventV1 {
+    /// A toll was collected for an action.
+    TollCollected {
+        /// Where the event was emitted.
+        block: BlockRefV1,
+        /// Who paid the toll.
+        from: EthAddress,
+        /// ETH amount, in wei.
+        amount: String,
+        /// Action identifier.
+        reason: String,
+    },
+    /// A buyback was executed.
+    BuybackExecuted {
+        /// Where the event was emitted.
+        block: BlockRefV1,
+        /// ETH spent, in wei.
+        eth_spent: String,
+        /// DATA bought, in wei.
+        data_received: String,
+        /// DATA burned, in wei.
+        data_burned: String,
+    },
+    /// Operations funds were withdrawn.
+    OperationsWithdrawn {
+        /// Where the event was emitted.
+        block: BlockRefV1,
+        /// Recipient.
+        to: EthAddress,
+        /// ETH amount, in wei.
+        amount: String,
+    },
+}
+
+impl FeeEventV1 {
+    /// DTO of `event`, or `None` if it is not published to `fees`.
+    #[must_use]
+    pub fn from_event(event: &GhostnetEvent) -> Option<Self> {
+        Some(match event {
+            GhostnetEvent::TollCollected(e) => Self::TollCollected {
+                block: (&e.meta).into(),
+                from: e.from.into(),
+                amount: decimal(e.amount),
+                reason: hex(&e.reason),
+            },
+            GhostnetEvent::BuybackExecuted(e) => Self::BuybackExecuted {
+                block: (&e.meta).into(),
+                eth_spent: decimal(e.eth_spent),
+                data_received: decimal(e.data_received),
+                data_burned: decimal(e.data_burned),
+            },
+            GhostnetEvent::OperationsWithdrawn(e) => Self::OperationsWithdrawn {
+                block: (&e.meta).into(),
+                to: e.to.into(),
+                amount: decimal(e.amount),
+            },
+            _ => return None,
+        })
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// DERIVED
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Event of the `derived` topic, version 1.
+///
+/// When the indexer derived the event is the envelope's timestamp.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum DerivedEventV1 {
+    /// A round's betting deadline is near.
+    RoundClosingSoon {
+        /// Round identifier.
+        round_id: String,
+        /// Threshold crossed, in seconds before the deadline.
+        threshold_secs: u64,
+        /// Seconds left until the deadline.
+        seconds_remaining: u64,
+        /// When betting closes.
+        deadline: DateTime<Utc>,
+    },
+    /// A round's deadline passed without it being resolved.
+    RoundAwaitingResolution {
+        /// Round identifier.
+        round_id: String,
+        /// When betting closed.
+        deadline: DateTime<Utc>,
+        /// Seconds past the deadline.
+        seconds_overdue: u64,
+    },
+    /// A boost stopped applying: it expired, or its position closed first.
+    BoostExpired {
+        /// Boost identifier.
+        boost_id: String,
+        /// User who had the boost.
+        user: EthAddress,
+        /// Type of boost.
+        boost_type: BoostType,
+        /// Boost value in basis points.
+        value_bps: u16,
+        /// When the boost was set to expire.
+        expiry: DateTime<Utc>,
+        /// When the boost stopped applying.
+        ended_at: DateTime<Utc>,
+        /// Whether the position closed before the boost expired.
+        position_closed: bool,
+    },
+}
+
+impl From<&DerivedEvent> for DerivedEventV1 {
+    fn from(event: &DerivedEvent) -> Self {
+        match event {
+            DerivedEvent::RoundClosingSoon(e) => Self::RoundClosingSoon {
+                round_id: decimal(e.round_id),
+                threshold_secs: e.threshold_secs,
+                seconds_remaining: e.seconds_remaining,
+                deadline: e.deadline,
+            },
+            DerivedEvent::RoundAwaitingResolution(e) => Self::RoundAwaitingResolution {
+                round_id: decimal(e.round_id),
+                deadline: e.deadline,
+                seconds_overdue: e.seconds_overdue,
+            },
+            DerivedEvent::BoostExpired(e) => Self::BoostExpired {
+                boost_id: e.boost_id.to_string(),
+                user: e.user.into(),
+                boost_type: e.boost_type,
+                value_bps: e.value_bps,
+                expiry: e.expiry,
+                ended_at: e.ended_at,
+                position_closed: e.position_closed,
+            },
+        }
+    }
+}
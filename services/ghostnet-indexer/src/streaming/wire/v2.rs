@@ -0,0 +1,130 @@
+//! Version 2 of the wire schemas.
+//!
+//! Only `positions` has a version 2: its amounts are decimal strings of
+//! DATA, like in the REST API, instead of wei.
+
+use alloy::primitives::U256;
+use serde::{Deserialize, Serialize};
+
+use super::v1::BlockRefV1;
+use crate::types::enums::{BoostType, Level};
+use crate::types::events::GhostnetEvent;
+use crate::types::primitives::{EthAddress, TokenAmount};
+
+/// Decimals of the DATA token.
+const DATA_TOKEN_DECIMALS: u8 = 18;
+
+/// Amount of DATA from wei.
+fn data(wei: U256) -> TokenAmount {
+    TokenAmount::from_wei(wei, DATA_TOKEN_DECIMALS)
+}
+
+/// Event of the `positions` topic, version 2.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum PositionEventV2 {
+    /// A user entered a position.
+    JackedIn {
+        /// Where the event was emitted.
+        block: BlockRefV1,
+        /// User's wallet address.
+        user: EthAddress,
+        /// DATA staked.
+        amount: TokenAmount,
+        /// Risk level chosen.
+        level: Level,
+        /// DATA staked after this action.
+        new_total: TokenAmount,
+    },
+    /// A user added to an existing position.
+    StakeAdded {
+        /// Where the event was emitted.
+        block: BlockRefV1,
+        /// User's wallet address.
+        user: EthAddress,
+        /// DATA added.
+        amount: TokenAmount,
+        /// DATA staked after this action.
+        new_total: TokenAmount,
+    },
+    /// A user extracted their position.
+    Extracted {
+        /// Where the event was emitted.
+        block: BlockRefV1,
+        /// User's wallet address.
+        user: EthAddress,
+        /// DATA principal returned.
+        amount: TokenAmount,
+        /// DATA rewards earned.
+        rewards: TokenAmount,
+    },
+    /// A position was culled due to level capacity.
+    PositionCulled {
+        /// Where the event was emitted.
+        block: BlockRefV1,
+        /// User who was culled.
+        victim: EthAddress,
+        /// DATA taken as penalty.
+        penalty_amount: TokenAmount,
+        /// DATA returned to the victim.
+        returned_amount: TokenAmount,
+        /// User who triggered the culling.
+        new_entrant: EthAddress,
+    },
+    /// A boost was applied to a user.
+    BoostApplied {
+        /// Where the event was emitted.
+        block: BlockRefV1,
+        /// User receiving the boost.
+        user: EthAddress,
+        /// Type of boost.
+        boost_type: BoostType,
+        /// Boost value in basis points.
+        value_bps: u16,
+        /// Unix timestamp when the boost expires.
+        expiry: u64,
+    },
+}
+
+impl PositionEventV2 {
+    /// DTO of `event`, or `None` if it is not published to `positions`.
+    #[must_use]
+    pub fn from_event(event: &GhostnetEvent) -> Option<Self> {
+        Some(match event {
+            GhostnetEvent::JackedIn(e) => Self::JackedIn {
+                block: (&e.meta).into(),
+                user: e.user.into(),
+                amount: data(e.amount),
+                level: e.level,
+                new_total: data(e.new_total),
+            },
+            GhostnetEvent::StakeAdded(e) => Self::StakeAdded {
+                block: (&e.meta).into(),
+                user: e.user.into(),
+                amount: data(e.amount),
+                new_total: data(e.new_total),
+            },
+            GhostnetEvent::Extracted(e) => Self::Extracted {
+                block: (&e.meta).into(),
+                user: e.user.into(),
+                amount: data(e.amount),
+                rewards: data(e.rewards),
+            },
+            GhostnetEvent::PositionCulled(e) => Self::PositionCulled {
+                block: (&e.meta).into(),
+                victim: e.victim.into(),
+                penalty_amount: data(e.penalty_amount),
+                returned_amount: data(e.returned_amount),
+                new_entrant: e.new_entrant.into(),
+            },
+            GhostnetEvent::BoostApplied(e) => Self::BoostApplied {
+                block: (&e.meta).into(),
+                user: e.user.into(),
+                boost_type: e.boost_type,
+                value_bps: e.value_bps,
+                expiry: e.expiry,
+            },
+            _ => return None,
+        })
+    }
+}
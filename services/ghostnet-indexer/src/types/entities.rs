@@ -4,11 +4,17 @@
 //! persisted to the database. They differ from events in that they represent
 //! current state rather than historical occurrences.
 
-use chrono::{DateTime, Utc};
+use std::collections::{BTreeMap, HashMap};
+
+use alloy::primitives::{Address, B256, Bytes, U256};
+use alloy::rpc::types::Log;
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, DurationRound, TimeDelta, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use super::enums::{BoostType, ExitReason, Level, RoundType};
+use super::events::{BoostAppliedEvent, EventMetadata, GhostnetEvent};
 use super::primitives::{BlockNumber, EthAddress, GhostStreak, TokenAmount};
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -29,6 +35,12 @@ pub struct Position {
     pub level: Level,
     /// Current staked amount.
     pub amount: TokenAmount,
+    /// Stake weighted by the yield multipliers active on the position, see
+    /// [`Position::apply_boosts`].
+    ///
+    /// Equal to `amount` without any, and when boosts are not tracked. Kept
+    /// as it was once the position closes.
+    pub effective_stake: TokenAmount,
     /// Accumulated reward debt (for reward calculations).
     pub reward_debt: TokenAmount,
     /// When the position was created.
@@ -45,6 +57,15 @@ pub struct Position {
     pub exit_reason: Option<ExitReason>,
     /// When the position was closed.
     pub exit_timestamp: Option<DateTime<Utc>>,
+    /// Seconds from entry to exit.
+    ///
+    /// `None` while the position is active, and for exits whose entry was
+    /// never indexed.
+    pub survival_seconds: Option<i64>,
+    /// Finalized scans at the position's level survived before exit.
+    ///
+    /// `None` in the same cases as `survival_seconds`.
+    pub scans_survived: Option<u32>,
     /// Amount returned on extraction.
     pub extracted_amount: Option<TokenAmount>,
     /// Rewards received on extraction.
@@ -56,11 +77,82 @@ pub struct Position {
 }
 
 impl Position {
+    /// Weight of a stake without boosts, in basis points.
+    pub const BASE_STAKE_WEIGHT_BPS: u32 = 10_000;
+
+    /// Create a closed position for an exit whose entry was never indexed.
+    ///
+    /// This happens when a backfill starts after the position was opened. The
+    /// level and entry time are unknown, so the level is [`Level::None`], the
+    /// exit time stands in for the entry time and the survival fields stay
+    /// `None`.
+    #[must_use]
+    pub fn unknown_entry(
+        user_address: EthAddress,
+        amount: TokenAmount,
+        reason: ExitReason,
+        exited_at: DateTime<Utc>,
+        block: BlockNumber,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            user_address,
+            level: Level::None,
+            reward_debt: TokenAmount::zero(),
+            entry_timestamp: exited_at,
+            last_add_timestamp: None,
+            ghost_streak: GhostStreak::ZERO,
+            is_alive: false,
+            is_extracted: reason == ExitReason::Extracted,
+            exit_reason: Some(reason),
+            exit_timestamp: Some(exited_at),
+            survival_seconds: None,
+            scans_survived: None,
+            extracted_amount: None,
+            extracted_rewards: None,
+            created_at_block: block,
+            updated_at: exited_at,
+            effective_stake: amount.clone(),
+            amount,
+        }
+    }
+
     /// Check if this position is currently active (alive and not extracted).
     #[must_use]
     pub const fn is_active(&self) -> bool {
         self.is_alive && !self.is_extracted
     }
+
+    /// Close the position, recording how long it survived.
+    ///
+    /// `is_extracted` and the extracted amounts are left to the caller.
+    pub fn close(&mut self, reason: ExitReason, exited_at: DateTime<Utc>, scans_survived: u32) {
+        self.is_alive = false;
+        self.exit_reason = Some(reason);
+        self.exit_timestamp = Some(exited_at);
+        // Clamped for out-of-order block timestamps
+        self.survival_seconds = Some((exited_at - self.entry_timestamp).num_seconds().max(0));
+        self.scans_survived = Some(scans_survived);
+        self.updated_at = exited_at;
+    }
+
+    /// Set the effective stake from `amount` and `boosts`, the boosts active
+    /// on the position.
+    ///
+    /// Yield multipliers stack additively on [`Self::BASE_STAKE_WEIGHT_BPS`]:
+    /// boosts of 2000 and 3000 bps make the stake count 1.5 times, rounded
+    /// half up to whole wei. Other boosts leave it unchanged.
+    ///
+    /// `GhostCore` records yield multipliers but pays rewards and cascades by
+    /// `amount`, so this is the weight the boosts stand for rather than one
+    /// the contract pays out by.
+    pub fn apply_boosts(&mut self, boosts: &[Boost]) {
+        let weight = boosts
+            .iter()
+            .map(Boost::stake_weight_bps)
+            .fold(Self::BASE_STAKE_WEIGHT_BPS, u32::saturating_add);
+        self.effective_stake = self.amount.percent_of(weight);
+    }
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -100,14 +192,69 @@ pub struct Scan {
     pub protocol_fee: Option<TokenAmount>,
     /// Number of survivors.
     pub survivor_count: Option<u32>,
+    /// Block the scan was finalized in.
+    pub finalized_block: Option<BlockNumber>,
+    /// Milliseconds between execution and finalization.
+    ///
+    /// Stays `None` until the scan finalizes, and for placeholder scans whose
+    /// execution time is unknown.
+    pub finalization_latency_ms: Option<u64>,
 }
 
 impl Scan {
+    /// Seed recorded for scans created before their `ScanExecuted` event.
+    pub const UNKNOWN_SEED: &'static str = "unknown";
+
+    /// Create a placeholder for a scan whose `ScanExecuted` event has not
+    /// been seen yet.
+    ///
+    /// `seen_at` stands in for the execution time until the real event fills
+    /// it in.
+    #[must_use]
+    pub fn placeholder(scan_id: String, level: Level, seen_at: DateTime<Utc>) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            scan_id,
+            level,
+            seed: Self::UNKNOWN_SEED.to_string(),
+            executed_at: seen_at,
+            finalized_at: None,
+            death_count: None,
+            total_dead: None,
+            burned: None,
+            distributed_same_level: None,
+            distributed_upstream: None,
+            protocol_fee: None,
+            survivor_count: None,
+            finalized_block: None,
+            finalization_latency_ms: None,
+        }
+    }
+
     /// Check if this scan has been finalized.
     #[must_use]
     pub const fn is_finalized(&self) -> bool {
         self.finalized_at.is_some()
     }
+
+    /// Check if this scan was created without its `ScanExecuted` event.
+    #[must_use]
+    pub fn is_placeholder(&self) -> bool {
+        self.seed == Self::UNKNOWN_SEED
+    }
+
+    /// Milliseconds from `executed_at` to `finalized_at`.
+    ///
+    /// Returns `None` if the scan is not finalized or is a placeholder.
+    /// Clock skew between the two timestamps clamps to zero.
+    #[must_use]
+    pub fn compute_finalization_latency_ms(&self) -> Option<u64> {
+        if self.is_placeholder() {
+            return None;
+        }
+        let elapsed = self.finalized_at? - self.executed_at;
+        Some(u64::try_from(elapsed.num_milliseconds()).unwrap_or(0))
+    }
 }
 
 /// Data for finalizing a scan (used by `ScanStore` port).
@@ -129,6 +276,62 @@ pub struct ScanFinalizationData {
     pub protocol_fee: TokenAmount,
     /// Number of survivors.
     pub survivor_count: u32,
+    /// Block the scan was finalized in.
+    pub finalized_block: BlockNumber,
+    /// Milliseconds since execution, if the execution time is known.
+    pub finalization_latency_ms: Option<u64>,
+}
+
+/// Where a [`ScanPrediction`] comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PredictionSource {
+    /// The next scan time scheduled on-chain.
+    Contract,
+    /// The intervals between recent scans.
+    History,
+}
+
+/// Observed cadence of a level's recent scans.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScanCadence {
+    /// When the most recent scan was executed.
+    pub last_scan_at: DateTime<Utc>,
+    /// Intervals between consecutive scans that were sampled.
+    pub intervals: u32,
+    /// Median interval in seconds.
+    pub median_interval_secs: i64,
+    /// 25th percentile interval in seconds.
+    pub p25_interval_secs: i64,
+    /// 75th percentile interval in seconds.
+    pub p75_interval_secs: i64,
+}
+
+/// Predicted time of a level's next scan.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScanPrediction {
+    /// Which level.
+    pub level: Level,
+    /// Where `next_scan_at` comes from.
+    pub source: PredictionSource,
+    /// Expected time of the next scan.
+    pub next_scan_at: DateTime<Utc>,
+    /// Start of the confidence band: the 25th percentile interval after the
+    /// last scan. Equals `next_scan_at` for on-chain predictions.
+    pub earliest_at: DateTime<Utc>,
+    /// End of the confidence band: the 75th percentile interval after the
+    /// last scan. Equals `next_scan_at` for on-chain predictions.
+    pub latest_at: DateTime<Utc>,
+    /// Cadence of recent scans (`None` with fewer than two scans).
+    pub cadence: Option<ScanCadence>,
+}
+
+impl ScanPrediction {
+    /// Whole seconds from `now` until `next_scan_at`, zero once it passed.
+    #[must_use]
+    pub fn seconds_remaining(&self, now: DateTime<Utc>) -> u64 {
+        u64::try_from((self.next_scan_at - now).num_seconds()).unwrap_or(0)
+    }
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -158,10 +361,81 @@ pub struct Death {
     pub created_at: DateTime<Utc>,
 }
 
+// ═══════════════════════════════════════════════════════════════════════════════
+// CASCADE REWARDS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// A surviving position's share of a cascade distribution.
+///
+/// `CascadeDistributed` only carries totals, so the indexer splits them
+/// between the positions alive at the time, pro rata to stake: the same-level
+/// amount between survivors on the source level, the upstream amount between
+/// all positions on safer levels.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CascadeShare {
+    /// Unique identifier.
+    pub id: Uuid,
+    /// Position that received the share.
+    pub position_id: Uuid,
+    /// Owner of the position.
+    pub user_address: EthAddress,
+    /// Level where the deaths occurred.
+    pub source_level: Level,
+    /// Level of the receiving position.
+    pub level: Level,
+    /// Amount received.
+    pub amount: TokenAmount,
+    /// Block of the distribution (the block the scan finalized in).
+    pub block_number: BlockNumber,
+    /// When the distribution happened.
+    pub created_at: DateTime<Utc>,
+}
+
+impl CascadeShare {
+    /// Whether the share came from deaths on a riskier level.
+    #[must_use]
+    pub fn is_upstream(&self) -> bool {
+        self.level != self.source_level
+    }
+}
+
+/// Cascade rewards an address earned from one scan.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScanCascadeEarnings {
+    /// On-chain scan ID, `None` until the scan's finalization is indexed.
+    pub scan_id: Option<String>,
+    /// Level that was scanned.
+    pub source_level: Level,
+    /// Amount earned from the scan.
+    pub amount: TokenAmount,
+    /// Block of the distribution.
+    pub block_number: BlockNumber,
+    /// When the distribution happened.
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Cascade rewards an address earned from other players' deaths.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CascadeEarnings {
+    /// Amount earned across all scans.
+    pub total: TokenAmount,
+    /// Earnings per scan, newest first.
+    pub scans: Vec<ScanCascadeEarnings>,
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // DEAD POOL (Prediction Market)
 // ═══════════════════════════════════════════════════════════════════════════════
 
+/// Share of each round's pot the `DeadPool` burns as rake, in basis points.
+pub const DEAD_POOL_RAKE_BPS: u64 = 500;
+
+/// Basis points of a whole.
+const BPS: u64 = 10_000;
+
+/// Decimals of the DATA token, which rounds are bet in.
+const DATA_DECIMALS: u8 = 18;
+
 /// Prediction market round.
 ///
 /// Users can bet on outcomes like death counts, whale deaths, etc.
@@ -191,6 +465,13 @@ pub struct Round {
     pub resolve_time: Option<DateTime<Utc>>,
     /// Total rake burned.
     pub total_burned: Option<TokenAmount>,
+    /// Multiple of their stake winning bets were paid, in basis points.
+    ///
+    /// `None` until resolved, and for rounds nobody bet on the outcome of.
+    pub payout_multiple_bps: Option<u64>,
+    /// What the bettors did not get back: the rake, plus the rest of the pot
+    /// if nobody bet on the outcome, as nobody can claim it.
+    pub house_take: Option<TokenAmount>,
 }
 
 impl Round {
@@ -205,6 +486,134 @@ impl Round {
     pub fn is_betting_open(&self, now: DateTime<Utc>) -> bool {
         !self.is_resolved && now < self.deadline
     }
+
+    /// Current pools of the round.
+    #[must_use]
+    pub fn pools(&self) -> Pools {
+        Pools {
+            over_pool: self.over_pool.clone(),
+            under_pool: self.under_pool.clone(),
+        }
+    }
+
+    /// Settlement of the round for a `RoundResolved` event.
+    ///
+    /// `total_pot` and `burned` are taken from the event; the winning pool
+    /// is the round's own.
+    #[must_use]
+    pub fn settle(
+        &self,
+        outcome: bool,
+        total_pot: &TokenAmount,
+        burned: &TokenAmount,
+        resolved_at: DateTime<Utc>,
+    ) -> RoundSettlement {
+        let net_pot = total_pot.saturating_sub(burned);
+        let winning_pool = self.pools().side(outcome).clone();
+        let house_take = if winning_pool.is_zero() {
+            total_pot.clone()
+        } else {
+            burned.clone()
+        };
+        RoundSettlement {
+            outcome,
+            burned: burned.clone(),
+            resolved_at,
+            payout_multiple_bps: ratio_bps(&net_pot, &winning_pool),
+            house_take,
+        }
+    }
+}
+
+/// Resolution of a round, see [`Round::settle`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoundSettlement {
+    /// Outcome (true = OVER won).
+    pub outcome: bool,
+    /// Rake burned.
+    pub burned: TokenAmount,
+    /// When the round was resolved.
+    pub resolved_at: DateTime<Utc>,
+    /// Multiple of their stake winning bets are paid, in basis points.
+    pub payout_multiple_bps: Option<u64>,
+    /// What the bettors do not get back.
+    pub house_take: TokenAmount,
+}
+
+/// OVER and UNDER pools of a round at one moment.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Pools {
+    /// Total bet on OVER.
+    pub over_pool: TokenAmount,
+    /// Total bet on UNDER.
+    pub under_pool: TokenAmount,
+}
+
+impl Pools {
+    /// Total bet on both sides.
+    #[must_use]
+    pub fn total(&self) -> TokenAmount {
+        self.over_pool.saturating_add(&self.under_pool)
+    }
+
+    /// Total bet on one side (true = OVER).
+    #[must_use]
+    pub const fn side(&self, is_over: bool) -> &TokenAmount {
+        if is_over {
+            &self.over_pool
+        } else {
+            &self.under_pool
+        }
+    }
+
+    /// The pools after a bet of `amount` on one side.
+    #[must_use]
+    pub fn with_bet(&self, is_over: bool, amount: &TokenAmount) -> Self {
+        let mut pools = self.clone();
+        if is_over {
+            pools.over_pool = pools.over_pool.saturating_add(amount);
+        } else {
+            pools.under_pool = pools.under_pool.saturating_add(amount);
+        }
+        pools
+    }
+
+    /// Probability the market gives one side, in basis points: the side's
+    /// share of the pot.
+    ///
+    /// `None` while nothing was bet on either side.
+    #[must_use]
+    pub fn implied_probability_bps(&self, is_over: bool) -> Option<u32> {
+        // A share of the pot is at most 10 000 bps
+        ratio_bps(self.side(is_over), &self.total()).map(|bps| bps.min(BPS) as u32)
+    }
+
+    /// Multiple of its stake a winning bet on one side is paid, in basis
+    /// points, if the round closes with these pools.
+    ///
+    /// Like the `DeadPool` contract, the pot less the rake is split between
+    /// the winning side pro rata, rounding down. `None` while nothing was bet
+    /// on the side.
+    #[must_use]
+    pub fn payout_multiple_bps(&self, is_over: bool) -> Option<u64> {
+        let total = self.total().to_wei(DATA_DECIMALS);
+        let rake = total.saturating_mul(U256::from(DEAD_POOL_RAKE_BPS)) / U256::from(BPS);
+        let net = TokenAmount::from_wei(total - rake, DATA_DECIMALS);
+        ratio_bps(&net, self.side(is_over))
+    }
+}
+
+/// `numerator / denominator` in basis points, rounded down.
+///
+/// `None` if `denominator` is zero; saturates at `u64::MAX`.
+fn ratio_bps(numerator: &TokenAmount, denominator: &TokenAmount) -> Option<u64> {
+    let numerator = numerator.to_wei(DATA_DECIMALS);
+    let denominator = denominator.to_wei(DATA_DECIMALS);
+    if denominator.is_zero() {
+        return None;
+    }
+    let bps = numerator.saturating_mul(U256::from(BPS)) / denominator;
+    Some(u64::try_from(bps).unwrap_or(u64::MAX))
 }
 
 /// User bet on a round.
@@ -220,6 +629,20 @@ pub struct Bet {
     pub amount: TokenAmount,
     /// Bet direction (true = OVER).
     pub is_over: bool,
+    /// Block the bet was placed in.
+    pub block_number: BlockNumber,
+    /// Index of the `BetPlaced` log in its block.
+    pub log_index: u64,
+    /// When the bet was placed.
+    pub placed_at: DateTime<Utc>,
+    /// Pools of the round right before the bet.
+    pub pools_before: Pools,
+    /// Probability the pools gave the bet's side right before it, in basis
+    /// points; `None` for the first bet of a round.
+    pub implied_probability_bps: Option<u32>,
+    /// Multiple of its stake the bet would be paid, in basis points, had the
+    /// round closed right after it.
+    pub payout_multiple_bps: Option<u64>,
     /// Whether winnings have been claimed.
     pub is_claimed: bool,
     /// Winnings (if won and claimed).
@@ -241,17 +664,69 @@ impl Bet {
     }
 }
 
+/// Implied odds of a round right after one of its bets.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OddsPoint {
+    /// Block of the bet.
+    pub block_number: BlockNumber,
+    /// Log index of the bet in its block.
+    pub log_index: u64,
+    /// When the bet was placed.
+    pub timestamp: DateTime<Utc>,
+    /// Pools after the bet.
+    #[serde(flatten)]
+    pub pools: Pools,
+    /// Probability of OVER, in basis points.
+    pub over_probability_bps: Option<u32>,
+    /// Probability of UNDER, in basis points.
+    pub under_probability_bps: Option<u32>,
+    /// Payout multiple of a winning OVER bet, in basis points.
+    pub over_payout_multiple_bps: Option<u64>,
+    /// Payout multiple of a winning UNDER bet, in basis points.
+    pub under_payout_multiple_bps: Option<u64>,
+}
+
+impl OddsPoint {
+    /// The odds after each of a round's bets, replayed in chain order from
+    /// empty pools.
+    #[must_use]
+    pub fn history(bets: &[Bet]) -> Vec<Self> {
+        let mut bets: Vec<&Bet> = bets.iter().collect();
+        bets.sort_by_key(|bet| (bet.block_number, bet.log_index));
+
+        let mut pools = Pools::default();
+        bets.into_iter()
+            .map(|bet| {
+                pools = pools.with_bet(bet.is_over, &bet.amount);
+                Self {
+                    block_number: bet.block_number,
+                    log_index: bet.log_index,
+                    timestamp: bet.placed_at,
+                    over_probability_bps: pools.implied_probability_bps(true),
+                    under_probability_bps: pools.implied_probability_bps(false),
+                    over_payout_multiple_bps: pools.payout_multiple_bps(true),
+                    under_payout_multiple_bps: pools.payout_multiple_bps(false),
+                    pools: pools.clone(),
+                }
+            })
+            .collect()
+    }
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // BOOST
 // ═══════════════════════════════════════════════════════════════════════════════
 
-/// Active boost on a user.
+/// Boost applied to a user's position.
 ///
-/// Boosts are temporary modifiers earned through mini-games.
+/// Boosts are temporary modifiers earned through mini-games. A boost applies
+/// until its expiry or until its position closes, whichever comes first.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Boost {
     /// Unique identifier.
     pub id: Uuid,
+    /// Position the boost was applied to.
+    pub position_id: Uuid,
     /// User with the boost.
     pub user_address: EthAddress,
     /// Type of boost.
@@ -260,15 +735,67 @@ pub struct Boost {
     pub value_bps: i16,
     /// When the boost expires.
     pub expiry: DateTime<Utc>,
+    /// Block of the `BoostApplied` event.
+    pub block_number: BlockNumber,
+    /// Index of the `BoostApplied` log in its block.
+    pub log_index: u64,
     /// When the boost was granted.
     pub created_at: DateTime<Utc>,
+    /// When the boost stopped applying, once recorded as expired.
+    pub expired_at: Option<DateTime<Utc>>,
 }
 
 impl Boost {
+    /// Create the boost a `BoostApplied` event applied to `position`.
+    ///
+    /// The event carries the expiry as a unix timestamp; one out of range
+    /// never expires.
+    #[must_use]
+    pub fn applied(position: &Position, event: &BoostAppliedEvent) -> Self {
+        let expiry = i64::try_from(event.expiry)
+            .ok()
+            .and_then(|secs| DateTime::from_timestamp(secs, 0))
+            .unwrap_or(DateTime::<Utc>::MAX_UTC);
+        Self {
+            id: Uuid::new_v4(),
+            position_id: position.id,
+            user_address: position.user_address,
+            boost_type: event.boost_type,
+            value_bps: i16::try_from(event.value_bps).unwrap_or(i16::MAX),
+            expiry,
+            block_number: BlockNumber::new(event.meta.block_number),
+            log_index: event.meta.log_index,
+            created_at: event.meta.timestamp,
+            expired_at: None,
+        }
+    }
+
     /// Check if this boost is still active.
     #[must_use]
     pub fn is_active(&self, now: DateTime<Utc>) -> bool {
-        now < self.expiry
+        self.expired_at.is_none() && now < self.expiry
+    }
+
+    /// Time left until the boost expires, zero once it is no longer active.
+    #[must_use]
+    pub fn remaining(&self, now: DateTime<Utc>) -> TimeDelta {
+        if self.is_active(now) {
+            self.expiry - now
+        } else {
+            TimeDelta::zero()
+        }
+    }
+
+    /// Basis points the boost adds to the weight of its position's stake.
+    ///
+    /// Zero unless its type [weights the stake](BoostType::weights_stake).
+    #[must_use]
+    pub fn stake_weight_bps(&self) -> u32 {
+        if self.boost_type.weights_stake() {
+            u32::from(self.value_bps.max(0).unsigned_abs())
+        } else {
+            0
+        }
     }
 
     /// Get the boost multiplier as a decimal (e.g., 0.35 for -35% death rate).
@@ -278,14 +805,69 @@ impl Boost {
     }
 }
 
+/// A boost that stopped applying but is not recorded as expired yet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LapsedBoost {
+    /// The boost.
+    pub boost: Boost,
+    /// When the boost's position closed, if it did.
+    pub position_closed_at: Option<DateTime<Utc>>,
+}
+
+impl LapsedBoost {
+    /// When the boost stopped applying: its expiry, or the exit of its
+    /// position if that came first.
+    #[must_use]
+    pub fn ended_at(&self) -> DateTime<Utc> {
+        self.position_closed_at
+            .map_or(self.boost.expiry, |closed| closed.min(self.boost.expiry))
+    }
+
+    /// Whether the boost's position closed before the boost expired.
+    #[must_use]
+    pub fn cut_short(&self) -> bool {
+        self.position_closed_at
+            .is_some_and(|closed| closed < self.boost.expiry)
+    }
+}
+
+/// An active position whose effective stake changed when its boosts were
+/// weighed again.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EffectiveStakeChange {
+    /// The position.
+    pub position_id: Uuid,
+    /// Level of the position.
+    pub level: Level,
+    /// Effective stake before.
+    pub previous: TokenAmount,
+    /// Effective stake now.
+    pub current: TokenAmount,
+}
+
+impl EffectiveStakeChange {
+    /// The level stats delta of the change.
+    #[must_use]
+    pub fn delta(&self) -> LevelStatsDelta {
+        LevelStatsDelta::effective_stake_changed(self.previous.clone(), self.current.clone())
+    }
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // POSITION HISTORY
 // ═══════════════════════════════════════════════════════════════════════════════
 
 /// Position history entry (for tracking changes over time).
+///
+/// One entry is appended for every event that changes a position. Amounts are
+/// unsigned; the direction of `amount_change` follows from the action:
+/// [`PositionAction::adds_stake`] actions add it to the stake,
+/// [`PositionAction::closes_position`] actions take the whole stake out of the
+/// position (leaving `new_total` at zero), and all others leave the stake as
+/// it was.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PositionHistoryEntry {
-    /// Unique identifier.
+    /// Unique identifier (UUIDv7, so IDs follow processing order).
     pub id: Uuid,
     /// Associated position.
     pub position_id: Uuid,
@@ -293,16 +875,191 @@ pub struct PositionHistoryEntry {
     pub user_address: EthAddress,
     /// What action occurred.
     pub action: PositionAction,
-    /// Change in amount (positive or negative).
+    /// Change in stake, see the type docs for its direction.
     pub amount_change: TokenAmount,
-    /// New total after action.
+    /// Stake left in the position after the action.
     pub new_total: TokenAmount,
+    /// Level of the position.
+    pub level: Level,
+    /// Ghost streak of the position after the action.
+    pub ghost_streak: GhostStreak,
     /// Block where this occurred.
     pub block_number: BlockNumber,
     /// When this occurred.
     pub timestamp: DateTime<Utc>,
 }
 
+impl PositionHistoryEntry {
+    /// Entry for `action` on `position`, taken after the action was applied.
+    ///
+    /// `new_total` is the position's stake, or zero if the action closes it.
+    #[must_use]
+    pub fn new(
+        position: &Position,
+        action: PositionAction,
+        amount_change: TokenAmount,
+        block_number: BlockNumber,
+        timestamp: DateTime<Utc>,
+    ) -> Self {
+        let new_total = if action.closes_position() {
+            TokenAmount::zero()
+        } else {
+            position.amount.clone()
+        };
+
+        Self {
+            id: Uuid::now_v7(),
+            position_id: position.id,
+            user_address: position.user_address,
+            action,
+            amount_change,
+            new_total,
+            level: position.level,
+            ghost_streak: position.ghost_streak,
+            block_number,
+            timestamp,
+        }
+    }
+}
+
+/// Place in a user's position history, for paging through it newest first.
+///
+/// History is ordered by timestamp, then ID. Formats as
+/// `<unix millis>_<entry id>`, which is what the API accepts as `before`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HistoryCursor {
+    /// Timestamp of the entry.
+    pub timestamp: DateTime<Utc>,
+    /// ID of the entry.
+    pub id: Uuid,
+}
+
+impl HistoryCursor {
+    /// Cursor pointing at `entry`.
+    #[must_use]
+    pub const fn of(entry: &PositionHistoryEntry) -> Self {
+        Self {
+            timestamp: entry.timestamp,
+            id: entry.id,
+        }
+    }
+}
+
+impl std::fmt::Display for HistoryCursor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}_{}", self.timestamp.timestamp_millis(), self.id)
+    }
+}
+
+impl std::str::FromStr for HistoryCursor {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Invalid history cursor: {s}");
+        let (millis, id) = s.split_once('_').ok_or_else(invalid)?;
+        let timestamp = millis
+            .parse()
+            .ok()
+            .and_then(DateTime::from_timestamp_millis)
+            .ok_or_else(invalid)?;
+        let id = id.parse().map_err(|_| invalid())?;
+        Ok(Self { timestamp, id })
+    }
+}
+
+/// Filter for listing positions across all users, newest first.
+///
+/// Positions are ordered by entry time, then ID. Every condition is optional;
+/// the default lists all positions, dead and extracted ones included.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PositionFilter {
+    /// Only positions at this level.
+    pub level: Option<Level>,
+    /// Only positions staking at least this much.
+    pub min_amount: Option<TokenAmount>,
+    /// Only positions staking at most this much.
+    pub max_amount: Option<TokenAmount>,
+    /// Only positions with at least this ghost streak.
+    pub min_streak: Option<GhostStreak>,
+    /// Only positions that are alive and not extracted.
+    pub active_only: bool,
+    /// Only positions entered after this time.
+    pub created_after: Option<DateTime<Utc>>,
+    /// Only positions after this cursor (a previous page's `next_cursor`).
+    pub after: Option<PositionCursor>,
+}
+
+impl PositionFilter {
+    /// Whether this lists the first page of all positions.
+    #[must_use]
+    pub const fn is_unfiltered(&self) -> bool {
+        self.level.is_none()
+            && self.min_amount.is_none()
+            && self.max_amount.is_none()
+            && self.min_streak.is_none()
+            && !self.active_only
+            && self.created_after.is_none()
+            && self.after.is_none()
+    }
+}
+
+/// Place in a listing of positions, for paging through it newest first.
+///
+/// Formats as `<unix millis>_<position id>` of the position's entry time,
+/// which is what the API accepts as `cursor`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PositionCursor {
+    /// Entry time of the position.
+    pub entry_timestamp: DateTime<Utc>,
+    /// ID of the position.
+    pub id: Uuid,
+}
+
+impl PositionCursor {
+    /// Cursor pointing at `position`.
+    #[must_use]
+    pub const fn of(position: &Position) -> Self {
+        Self {
+            entry_timestamp: position.entry_timestamp,
+            id: position.id,
+        }
+    }
+}
+
+impl std::fmt::Display for PositionCursor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}_{}", self.entry_timestamp.timestamp_millis(), self.id)
+    }
+}
+
+impl std::str::FromStr for PositionCursor {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Invalid position cursor: {s}");
+        let (millis, id) = s.split_once('_').ok_or_else(invalid)?;
+        let entry_timestamp = millis
+            .parse()
+            .ok()
+            .and_then(DateTime::from_timestamp_millis)
+            .ok_or_else(invalid)?;
+        let id = id.parse().map_err(|_| invalid())?;
+        Ok(Self {
+            entry_timestamp,
+            id,
+        })
+    }
+}
+
+/// One page of a listing.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Page<T> {
+    /// Items on this page.
+    pub items: Vec<T>,
+    /// Cursor for the next page, `None` on the last one.
+    pub next_cursor: Option<String>,
+}
+
 /// Actions that can be recorded in position history.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[non_exhaustive]
@@ -317,6 +1074,8 @@ pub enum PositionAction {
     Traced,
     /// Position was culled.
     Culled,
+    /// A mini-game boost was applied to the position.
+    BoostApplied,
     /// Position was closed in system reset.
     SystemReset,
     /// User claimed accumulated rewards.
@@ -338,11 +1097,52 @@ impl PositionAction {
             Self::Extracted => "Extracted",
             Self::Traced => "Traced",
             Self::Culled => "Culled",
+            Self::BoostApplied => "Boost Applied",
             Self::SystemReset => "System Reset",
             Self::RewardsClaimed => "Rewards Claimed",
             Self::Superseded => "Superseded",
         }
     }
+
+    /// Whether the action adds its amount to the position's stake.
+    #[must_use]
+    pub const fn adds_stake(&self) -> bool {
+        matches!(self, Self::JackedIn | Self::StakeAdded)
+    }
+
+    /// Whether the action ends the position, taking out its whole stake.
+    #[must_use]
+    pub const fn closes_position(&self) -> bool {
+        matches!(
+            self,
+            Self::Extracted | Self::Traced | Self::Culled | Self::SystemReset | Self::Superseded
+        )
+    }
+}
+
+impl std::fmt::Display for PositionAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl std::str::FromStr for PositionAction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Jacked In" | "JackedIn" => Ok(Self::JackedIn),
+            "Stake Added" | "StakeAdded" => Ok(Self::StakeAdded),
+            "Extracted" => Ok(Self::Extracted),
+            "Traced" => Ok(Self::Traced),
+            "Culled" => Ok(Self::Culled),
+            "Boost Applied" | "BoostApplied" => Ok(Self::BoostApplied),
+            "System Reset" | "SystemReset" => Ok(Self::SystemReset),
+            "Rewards Claimed" | "RewardsClaimed" => Ok(Self::RewardsClaimed),
+            "Superseded" => Ok(Self::Superseded),
+            _ => Err(format!("Unknown position action: {s}")),
+        }
+    }
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -356,77 +1156,1175 @@ pub struct LevelStats {
     pub level: Level,
     /// Total DATA staked at this level.
     pub total_staked: TokenAmount,
+    /// Total effective stake of the active positions at this level, see
+    /// [`Position::effective_stake`].
+    pub total_effective_staked: TokenAmount,
     /// Number of active positions.
     pub alive_count: u32,
     /// Total deaths ever at this level.
     pub total_deaths: u32,
+    /// Deaths recorded within the last 24 hours.
+    pub deaths_24h: u32,
     /// Total positions that extracted.
     pub total_extracted: u32,
+    /// Total positions culled by level capacity.
+    pub total_culled: u32,
     /// Total DATA burned from deaths.
     pub total_burned: TokenAmount,
     /// Total DATA distributed to survivors.
     pub total_distributed: TokenAmount,
     /// Highest ghost streak achieved at this level.
     pub highest_ghost_streak: GhostStreak,
+    /// Sum of ghost streaks across active positions.
+    pub total_ghost_streak: u64,
+    /// Sum of survival times of exited positions, in seconds.
+    pub total_survival_seconds: u64,
+    /// Exited positions with a known survival time.
+    pub survival_samples: u32,
     /// Last update time.
     pub updated_at: DateTime<Utc>,
 }
 
-/// Delta for updating level statistics.
-///
-/// Use this to atomically update stats without race conditions.
-#[derive(Debug, Clone, Default)]
-pub struct LevelStatsDelta {
-    /// Change in total staked.
-    pub staked_delta: Option<TokenAmount>,
-    /// Change in alive count (can be negative).
-    pub alive_delta: Option<i32>,
-    /// Increment in death count.
-    pub deaths_delta: Option<u32>,
-    /// Increment in extraction count.
-    pub extracted_delta: Option<u32>,
-    /// Increment in burned amount.
-    pub burned_delta: Option<TokenAmount>,
-    /// Increment in distributed amount.
-    pub distributed_delta: Option<TokenAmount>,
-    /// New highest streak (if higher than current).
-    pub new_highest_streak: Option<GhostStreak>,
-}
-
-/// Global protocol statistics.
+impl LevelStats {
+    /// Create empty statistics for a level.
+    #[must_use]
+    pub fn empty(level: Level, updated_at: DateTime<Utc>) -> Self {
+        Self {
+            level,
+            total_staked: TokenAmount::zero(),
+            total_effective_staked: TokenAmount::zero(),
+            alive_count: 0,
+            total_deaths: 0,
+            deaths_24h: 0,
+            total_extracted: 0,
+            total_culled: 0,
+            total_burned: TokenAmount::zero(),
+            total_distributed: TokenAmount::zero(),
+            highest_ghost_streak: GhostStreak::ZERO,
+            total_ghost_streak: 0,
+            total_survival_seconds: 0,
+            survival_samples: 0,
+            updated_at,
+        }
+    }
+
+    /// Average ghost streak across active positions.
+    ///
+    /// Returns `0.0` when the level has no active positions.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)] // Display value, precision loss is acceptable
+    pub fn average_ghost_streak(&self) -> f64 {
+        if self.alive_count == 0 {
+            0.0
+        } else {
+            self.total_ghost_streak as f64 / f64::from(self.alive_count)
+        }
+    }
+
+    /// Average survival time of exited positions, in seconds.
+    ///
+    /// Returns `None` when no exit with a known entry has been recorded.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)] // Display value, precision loss is acceptable
+    pub fn average_survival_seconds(&self) -> Option<f64> {
+        (self.survival_samples > 0)
+            .then(|| self.total_survival_seconds as f64 / f64::from(self.survival_samples))
+    }
+
+    /// Share of exits that were culls, from `0.0` to `1.0`.
+    ///
+    /// Exits are extractions, culls and deaths. Returns `0.0` when the level
+    /// has no exits.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)] // Display value, precision loss is acceptable
+    pub fn cull_rate(&self) -> f64 {
+        let exits = u64::from(self.total_extracted)
+            + u64::from(self.total_culled)
+            + u64::from(self.total_deaths);
+        if exits == 0 {
+            0.0
+        } else {
+            f64::from(self.total_culled) / exits as f64
+        }
+    }
+
+    /// Apply a delta to these statistics.
+    ///
+    /// Counters saturate at zero rather than underflowing. `deaths_24h` is a
+    /// windowed value and is left to the caller.
+    pub fn apply(&mut self, delta: &LevelStatsDelta, at: DateTime<Utc>) {
+        if let Some(staked) = &delta.staked_delta {
+            self.total_staked = self.total_staked.saturating_add(staked);
+        }
+        if let Some(unstaked) = &delta.unstaked_delta {
+            self.total_staked = self.total_staked.saturating_sub(unstaked);
+        }
+        if let Some(staked) = &delta.effective_staked_delta {
+            self.total_effective_staked = self.total_effective_staked.saturating_add(staked);
+        }
+        if let Some(unstaked) = &delta.effective_unstaked_delta {
+            self.total_effective_staked = self.total_effective_staked.saturating_sub(unstaked);
+        }
+        if let Some(alive) = delta.alive_delta {
+            self.alive_count = self.alive_count.saturating_add_signed(alive);
+        }
+        if let Some(deaths) = delta.deaths_delta {
+            self.total_deaths = self.total_deaths.saturating_add(deaths);
+        }
+        if let Some(extracted) = delta.extracted_delta {
+            self.total_extracted = self.total_extracted.saturating_add(extracted);
+        }
+        if let Some(culled) = delta.culled_delta {
+            self.total_culled = self.total_culled.saturating_add(culled);
+        }
+        if let Some(burned) = &delta.burned_delta {
+            self.total_burned = self.total_burned.saturating_add(burned);
+        }
+        if let Some(distributed) = &delta.distributed_delta {
+            self.total_distributed = self.total_distributed.saturating_add(distributed);
+        }
+        if let Some(streak) = delta.new_highest_streak {
+            self.highest_ghost_streak = self.highest_ghost_streak.max(streak);
+        }
+        if let Some(streak) = delta.streak_delta {
+            self.total_ghost_streak = self.total_ghost_streak.saturating_add_signed(streak);
+        }
+        if let Some(seconds) = delta.survival_seconds_delta {
+            self.total_survival_seconds =
+                self.total_survival_seconds.saturating_add_signed(seconds);
+        }
+        if let Some(samples) = delta.survival_samples_delta {
+            self.survival_samples = self.survival_samples.saturating_add(samples);
+        }
+        self.updated_at = at;
+    }
+}
+
+/// Delta for updating level statistics.
+///
+/// Use this to atomically update stats without race conditions.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LevelStatsDelta {
+    /// Increase in total staked.
+    pub staked_delta: Option<TokenAmount>,
+    /// Decrease in total staked.
+    pub unstaked_delta: Option<TokenAmount>,
+    /// Increase in total effective stake.
+    pub effective_staked_delta: Option<TokenAmount>,
+    /// Decrease in total effective stake.
+    pub effective_unstaked_delta: Option<TokenAmount>,
+    /// Change in alive count (can be negative).
+    pub alive_delta: Option<i32>,
+    /// Increment in death count.
+    pub deaths_delta: Option<u32>,
+    /// Increment in extraction count.
+    pub extracted_delta: Option<u32>,
+    /// Increment in cull count.
+    pub culled_delta: Option<u32>,
+    /// Increment in burned amount.
+    pub burned_delta: Option<TokenAmount>,
+    /// Increment in distributed amount.
+    pub distributed_delta: Option<TokenAmount>,
+    /// New highest streak (if higher than current).
+    pub new_highest_streak: Option<GhostStreak>,
+    /// Change in the ghost streak sum across active positions.
+    pub streak_delta: Option<i64>,
+    /// Increment in the survival time sum, in seconds.
+    pub survival_seconds_delta: Option<i64>,
+    /// Increment in the number of survival samples.
+    pub survival_samples_delta: Option<u32>,
+}
+
+impl LevelStatsDelta {
+    /// Delta for a newly opened position.
+    #[must_use]
+    pub fn opened(position: &Position) -> Self {
+        Self {
+            staked_delta: Some(position.amount.clone()),
+            effective_staked_delta: Some(position.effective_stake.clone()),
+            alive_delta: Some(1),
+            new_highest_streak: Some(position.ghost_streak),
+            streak_delta: Some(i64::from(position.ghost_streak.value())),
+            ..Self::default()
+        }
+    }
+
+    /// Delta for an active position whose stake changed from `previous` to `current`.
+    #[must_use]
+    pub fn stake_changed(previous: TokenAmount, current: TokenAmount) -> Self {
+        Self {
+            staked_delta: Some(current),
+            unstaked_delta: Some(previous),
+            ..Self::default()
+        }
+    }
+
+    /// Delta for an active position whose effective stake changed from
+    /// `previous` to `current`.
+    #[must_use]
+    pub fn effective_stake_changed(previous: TokenAmount, current: TokenAmount) -> Self {
+        Self {
+            effective_staked_delta: Some(current),
+            effective_unstaked_delta: Some(previous),
+            ..Self::default()
+        }
+    }
+
+    /// Delta for a position leaving the active set without dying
+    /// (superseded, or the base of the other exits).
+    ///
+    /// The survival time is recorded if the position has one.
+    #[must_use]
+    pub fn closed(position: &Position) -> Self {
+        Self {
+            unstaked_delta: Some(position.amount.clone()),
+            effective_unstaked_delta: Some(position.effective_stake.clone()),
+            alive_delta: Some(-1),
+            new_highest_streak: Some(position.ghost_streak),
+            streak_delta: Some(-i64::from(position.ghost_streak.value())),
+            survival_seconds_delta: position.survival_seconds,
+            survival_samples_delta: position.survival_seconds.map(|_| 1),
+            ..Self::default()
+        }
+    }
+
+    /// Delta for a position culled by level capacity.
+    #[must_use]
+    pub fn culled(position: &Position) -> Self {
+        Self {
+            culled_delta: Some(1),
+            ..Self::closed(position)
+        }
+    }
+
+    /// Delta for a voluntarily extracted position.
+    #[must_use]
+    pub fn extracted(position: &Position) -> Self {
+        Self {
+            extracted_delta: Some(1),
+            ..Self::closed(position)
+        }
+    }
+
+    /// Delta for a position that died.
+    #[must_use]
+    pub fn died(position: &Position) -> Self {
+        Self {
+            deaths_delta: Some(1),
+            ..Self::closed(position)
+        }
+    }
+
+    /// Delta for a cascade distribution out of this level's deaths.
+    #[must_use]
+    pub fn cascade(burned: TokenAmount, distributed: TokenAmount) -> Self {
+        Self {
+            burned_delta: Some(burned),
+            distributed_delta: Some(distributed),
+            ..Self::default()
+        }
+    }
+
+    /// Check if this delta changes nothing.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Fold another delta into this one.
+    pub fn merge(&mut self, other: Self) {
+        merge_amount(&mut self.staked_delta, other.staked_delta);
+        merge_amount(&mut self.unstaked_delta, other.unstaked_delta);
+        merge_amount(&mut self.effective_staked_delta, other.effective_staked_delta);
+        merge_amount(&mut self.effective_unstaked_delta, other.effective_unstaked_delta);
+        merge_amount(&mut self.burned_delta, other.burned_delta);
+        merge_amount(&mut self.distributed_delta, other.distributed_delta);
+        merge_with(&mut self.alive_delta, other.alive_delta, i32::saturating_add);
+        merge_with(&mut self.deaths_delta, other.deaths_delta, u32::saturating_add);
+        merge_with(&mut self.extracted_delta, other.extracted_delta, u32::saturating_add);
+        merge_with(&mut self.culled_delta, other.culled_delta, u32::saturating_add);
+        merge_with(&mut self.new_highest_streak, other.new_highest_streak, Ord::max);
+        merge_with(&mut self.streak_delta, other.streak_delta, i64::saturating_add);
+        merge_with(
+            &mut self.survival_seconds_delta,
+            other.survival_seconds_delta,
+            i64::saturating_add,
+        );
+        merge_with(
+            &mut self.survival_samples_delta,
+            other.survival_samples_delta,
+            u32::saturating_add,
+        );
+    }
+}
+
+/// Global protocol statistics.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GlobalStats {
+    /// Total DATA locked across all levels.
+    pub total_value_locked: TokenAmount,
+    /// Total active positions.
+    pub total_positions: u32,
+    /// Total deaths ever.
+    pub total_deaths: u32,
+    /// Total DATA burned.
+    pub total_burned: TokenAmount,
+    /// Total emissions distributed.
+    pub total_emissions_distributed: TokenAmount,
+    /// Total toll collected (ETH).
+    pub total_toll_collected: TokenAmount,
+    /// Total DATA burned via buyback.
+    pub total_buyback_burned: TokenAmount,
+    /// Number of system resets.
+    pub system_reset_count: u32,
+    /// Last update time.
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Delta for updating the event-sourced global counters.
+///
+/// Totals derived from level statistics (TVL, positions, deaths) are
+/// recomputed by [`StatsStore::refresh_global_stats`] instead.
+///
+/// [`StatsStore::refresh_global_stats`]: crate::ports::StatsStore::refresh_global_stats
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GlobalStatsDelta {
+    /// Increment in DATA burned.
+    pub burned_delta: Option<TokenAmount>,
+    /// Increment in emissions distributed.
+    pub emissions_delta: Option<TokenAmount>,
+    /// Increment in toll collected.
+    pub toll_delta: Option<TokenAmount>,
+    /// Increment in DATA burned via buyback.
+    pub buyback_burned_delta: Option<TokenAmount>,
+    /// Increment in system reset count.
+    pub system_resets_delta: Option<u32>,
+}
+
+impl GlobalStatsDelta {
+    /// Check if this delta changes nothing.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Fold another delta into this one.
+    pub fn merge(&mut self, other: Self) {
+        merge_amount(&mut self.burned_delta, other.burned_delta);
+        merge_amount(&mut self.emissions_delta, other.emissions_delta);
+        merge_amount(&mut self.toll_delta, other.toll_delta);
+        merge_amount(&mut self.buyback_burned_delta, other.buyback_burned_delta);
+        merge_with(
+            &mut self.system_resets_delta,
+            other.system_resets_delta,
+            u32::saturating_add,
+        );
+    }
+
+    /// Apply this delta to global statistics.
+    pub fn apply_to(&self, stats: &mut GlobalStats) {
+        if let Some(burned) = &self.burned_delta {
+            stats.total_burned = stats.total_burned.saturating_add(burned);
+        }
+        if let Some(emissions) = &self.emissions_delta {
+            stats.total_emissions_distributed =
+                stats.total_emissions_distributed.saturating_add(emissions);
+        }
+        if let Some(toll) = &self.toll_delta {
+            stats.total_toll_collected = stats.total_toll_collected.saturating_add(toll);
+        }
+        if let Some(buyback) = &self.buyback_burned_delta {
+            stats.total_buyback_burned = stats.total_buyback_burned.saturating_add(buyback);
+        }
+        if let Some(resets) = self.system_resets_delta {
+            stats.system_reset_count = stats.system_reset_count.saturating_add(resets);
+        }
+    }
+}
+
+/// Fold an optional amount into an accumulator.
+fn merge_amount(acc: &mut Option<TokenAmount>, other: Option<TokenAmount>) {
+    merge_with(acc, other, |a, b| a.saturating_add(&b));
+}
+
+/// Fold an optional value into an accumulator with `combine`.
+fn merge_with<T>(acc: &mut Option<T>, other: Option<T>, combine: impl FnOnce(T, T) -> T) {
+    if let Some(value) = other {
+        *acc = Some(match acc.take() {
+            Some(current) => combine(current, value),
+            None => value,
+        });
+    }
+}
+
+/// Survival analytics of the positions that exited a level.
+///
+/// Exits are extractions, culls, traces and system resets. Superseded
+/// positions and exits whose entry was never indexed are not attributed to a
+/// level and are left out.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LevelSurvival {
+    /// Which level.
+    pub level: Level,
+    /// Positions that exited the level.
+    pub exits: u32,
+    /// Exits that were culls.
+    pub culled: u32,
+    /// Share of exits that were culls, from `0.0` to `1.0`.
+    pub cull_rate: f64,
+    /// Average seconds from entry to exit (`None` without exits).
+    pub average_survival_seconds: Option<f64>,
+    /// Average finalized scans survived before exit (`None` without exits).
+    pub average_scans_survived: Option<f64>,
+}
+
+/// Number of exits from a level at a given ghost streak.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExitStreakCount {
+    /// Level the positions exited.
+    pub level: Level,
+    /// Ghost streak at exit.
+    pub ghost_streak: GhostStreak,
+    /// Positions that exited with this streak.
+    pub exits: u32,
+}
+
+/// Leaderboard entry.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LeaderboardEntry {
+    /// Position on the leaderboard (1-indexed).
+    pub rank: u32,
+    /// User address.
+    pub user_address: EthAddress,
+    /// Score (interpretation depends on leaderboard type).
+    pub score: TokenAmount,
+    /// Additional metadata (JSON).
+    pub metadata: Option<serde_json::Value>,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// LEVEL HISTORY
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Bucket size of a level history series.
+///
+/// Only these sizes are served, which keeps series queries bounded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum HistoryBucket {
+    /// Five minutes.
+    #[serde(rename = "5m")]
+    FiveMinutes,
+    /// One hour.
+    #[serde(rename = "1h")]
+    Hour,
+    /// One day.
+    #[serde(rename = "1d")]
+    Day,
+}
+
+impl HistoryBucket {
+    /// All bucket sizes, shortest first.
+    pub const ALL: [Self; 3] = [Self::FiveMinutes, Self::Hour, Self::Day];
+
+    /// Name of the size in API queries.
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::FiveMinutes => "5m",
+            Self::Hour => "1h",
+            Self::Day => "1d",
+        }
+    }
+
+    /// Length of a bucket.
+    #[must_use]
+    pub const fn duration(&self) -> TimeDelta {
+        match self {
+            Self::FiveMinutes => TimeDelta::minutes(5),
+            Self::Hour => TimeDelta::hours(1),
+            Self::Day => TimeDelta::days(1),
+        }
+    }
+
+    /// Start of the bucket containing `at`.
+    ///
+    /// Buckets are aligned to the Unix epoch, as `TimescaleDB`'s
+    /// `time_bucket` aligns them for these sizes.
+    #[must_use]
+    pub fn start_of(&self, at: DateTime<Utc>) -> DateTime<Utc> {
+        at.duration_trunc(self.duration()).unwrap_or(at)
+    }
+}
+
+impl std::fmt::Display for HistoryBucket {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for HistoryBucket {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|bucket| bucket.as_str() == s)
+            .ok_or_else(|| format!("Unknown bucket size: {s} (expected 5m, 1h or 1d)"))
+    }
+}
+
+/// Outcome of one finalized scan at its level.
+///
+/// Written once per scan, so a level's death rate can be charted over time.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LevelScanStats {
+    /// On-chain scan ID.
+    pub scan_id: String,
+    /// Level that was scanned.
+    pub level: Level,
+    /// When the scan was finalized.
+    pub scanned_at: DateTime<Utc>,
+    /// Block of the finalization, if known.
+    pub block_number: Option<BlockNumber>,
+    /// Positions traced by the scan.
+    pub deaths: u32,
+    /// Positions alive at the level after the scan.
+    pub survivors: u32,
+    /// DATA staked at the level after the scan.
+    pub total_staked: TokenAmount,
+}
+
+/// One bucket of a level's history.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LevelHistoryPoint {
+    /// Start of the bucket.
+    pub timestamp: DateTime<Utc>,
+    /// Scans finalized in the bucket.
+    pub scans: u32,
+    /// Positions traced by those scans.
+    pub deaths: u32,
+    /// Share of the positions scanned in the bucket that died, from `0.0` to
+    /// `1.0` (`None` without scans of a populated level).
+    pub death_rate: Option<f64>,
+    /// Positions alive after the latest scan up to this bucket (`None`
+    /// before the first scan of the series).
+    pub survivor_count: Option<u32>,
+    /// DATA staked after the latest scan up to this bucket (`None` before
+    /// the first scan of the series).
+    pub tvl: Option<TokenAmount>,
+}
+
+impl LevelHistoryPoint {
+    /// Point of a bucket from the totals of its scans.
+    ///
+    /// `scanned` is the number of positions the scans saw, deaths plus
+    /// survivors; `survivors` and `tvl` are those of the bucket's last scan.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)] // Acceptable for a rate
+    pub fn from_totals(
+        timestamp: DateTime<Utc>,
+        scans: u32,
+        deaths: u32,
+        scanned: u64,
+        latest: (u32, TokenAmount),
+    ) -> Self {
+        Self {
+            timestamp,
+            scans,
+            deaths,
+            death_rate: (scanned > 0).then(|| f64::from(deaths) / scanned as f64),
+            survivor_count: Some(latest.0),
+            tvl: Some(latest.1),
+        }
+    }
+
+    /// Series of `scans` in `bucket`-sized buckets from `from` to `to`.
+    ///
+    /// Scans finalized in `[from, to)` are counted. The series starts with
+    /// the bucket containing `from` and ends with the last bucket starting
+    /// before `to`; see [`Self::fill_gaps`] for buckets without scans.
+    #[must_use]
+    pub fn series(
+        scans: &[LevelScanStats],
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        bucket: HistoryBucket,
+    ) -> Vec<Self> {
+        let mut in_range: Vec<_> = scans
+            .iter()
+            .filter(|scan| scan.scanned_at >= from && scan.scanned_at < to)
+            .collect();
+        in_range.sort_by_key(|scan| scan.scanned_at);
+
+        let mut buckets: BTreeMap<DateTime<Utc>, (u32, u32, u64, &LevelScanStats)> =
+            BTreeMap::new();
+        for scan in in_range {
+            let totals = buckets
+                .entry(bucket.start_of(scan.scanned_at))
+                .or_insert((0, 0, 0, scan));
+            totals.0 += 1;
+            totals.1 = totals.1.saturating_add(scan.deaths);
+            totals.2 += u64::from(scan.deaths) + u64::from(scan.survivors);
+            totals.3 = scan;
+        }
+
+        let points = buckets
+            .into_iter()
+            .map(|(start, (count, deaths, scanned, last))| {
+                let latest = (last.survivors, last.total_staked.clone());
+                Self::from_totals(start, count, deaths, scanned, latest)
+            })
+            .collect();
+        Self::fill_gaps(points, from, to, bucket)
+    }
+
+    /// Complete the buckets with scans, `points` in time order, into a
+    /// series of every bucket from `from` to `to`.
+    ///
+    /// A bucket without scans has no deaths and no death rate, and carries
+    /// the survivors and TVL of the bucket before it forward.
+    #[must_use]
+    pub fn fill_gaps(
+        points: Vec<Self>,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        bucket: HistoryBucket,
+    ) -> Vec<Self> {
+        let mut points = points.into_iter().peekable();
+        let mut series = Vec::new();
+        let mut latest = (None, None);
+        let mut start = bucket.start_of(from);
+        while start < to {
+            while points.next_if(|point| point.timestamp < start).is_some() {}
+            let point = points.next_if(|point| point.timestamp == start).unwrap_or_else(|| Self {
+                timestamp: start,
+                scans: 0,
+                deaths: 0,
+                death_rate: None,
+                survivor_count: latest.0,
+                tvl: latest.1.clone(),
+            });
+            latest = (point.survivor_count, point.tvl.clone());
+            series.push(point);
+            start += bucket.duration();
+        }
+        series
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TOKEN FLOWS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Raw `DataToken` transfer.
+///
+/// Only persisted when `token_flows.persist_transfers` is enabled; the
+/// aggregates in [`TokenFlowDelta`] are maintained either way.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenTransfer {
+    /// Sender (zero address for mints).
+    pub from_address: EthAddress,
+    /// Recipient (dead address for burns).
+    pub to_address: EthAddress,
+    /// Amount transferred.
+    pub amount: TokenAmount,
+    /// Block containing the transfer.
+    pub block_number: BlockNumber,
+    /// Transaction hash.
+    pub tx_hash: [u8; 32],
+    /// Log index within the block.
+    pub log_index: u64,
+    /// Block timestamp.
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Change to the token flow aggregates.
+///
+/// Deltas are merged per hourly bucket before they are written, so windowed
+/// queries have hour granularity.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TokenFlowDelta {
+    /// DATA sent to the dead address (includes tax burns).
+    pub burned: Option<TokenAmount>,
+    /// DATA burned by the transfer tax.
+    pub tax_burned: Option<TokenAmount>,
+    /// DATA collected to the treasury by the transfer tax.
+    pub tax_collected: Option<TokenAmount>,
+    /// DATA moved by transfers of any kind.
+    pub volume: Option<TokenAmount>,
+    /// Number of transfers.
+    pub transfers: u32,
+    /// Inflow and outflow per address.
+    pub addresses: HashMap<EthAddress, AddressFlowDelta>,
+}
+
+impl TokenFlowDelta {
+    /// Check if this delta changes nothing.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Fold another delta into this one.
+    pub fn merge(&mut self, other: Self) {
+        merge_amount(&mut self.burned, other.burned);
+        merge_amount(&mut self.tax_burned, other.tax_burned);
+        merge_amount(&mut self.tax_collected, other.tax_collected);
+        merge_amount(&mut self.volume, other.volume);
+        self.transfers = self.transfers.saturating_add(other.transfers);
+        for (address, flow) in other.addresses {
+            self.addresses.entry(address).or_default().merge(&flow);
+        }
+    }
+}
+
+/// Change to a single address's token flows.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AddressFlowDelta {
+    /// DATA received.
+    pub inflow: TokenAmount,
+    /// DATA sent.
+    pub outflow: TokenAmount,
+    /// Number of transfers received.
+    pub transfers_in: u32,
+    /// Number of transfers sent.
+    pub transfers_out: u32,
+}
+
+impl AddressFlowDelta {
+    /// Fold another delta into this one.
+    pub fn merge(&mut self, other: &Self) {
+        self.inflow = self.inflow.saturating_add(&other.inflow);
+        self.outflow = self.outflow.saturating_add(&other.outflow);
+        self.transfers_in = self.transfers_in.saturating_add(other.transfers_in);
+        self.transfers_out = self.transfers_out.saturating_add(other.transfers_out);
+    }
+}
+
+/// Token burn and tax totals over a time window.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BurnRate {
+    /// Window length in seconds.
+    pub window_secs: u64,
+    /// DATA burned in the window (includes tax burns).
+    pub burned: TokenAmount,
+    /// DATA burned by the transfer tax in the window.
+    pub tax_burned: TokenAmount,
+    /// DATA collected to the treasury in the window.
+    pub tax_collected: TokenAmount,
+    /// Average DATA burned per hour over the window.
+    pub burned_per_hour: TokenAmount,
+}
+
+impl BurnRate {
+    /// Build a burn rate from window totals.
+    #[must_use]
+    pub fn new(
+        window: std::time::Duration,
+        burned: TokenAmount,
+        tax_burned: TokenAmount,
+        tax_collected: TokenAmount,
+    ) -> Self {
+        let window_secs = window.as_secs();
+        let burned_per_hour = if window_secs == 0 {
+            TokenAmount::zero()
+        } else {
+            let per_hour = (burned.as_decimal() * BigDecimal::from(3600_u32)
+                / BigDecimal::from(window_secs))
+            .with_scale(18)
+            .normalized();
+            TokenAmount::new(per_hour).unwrap_or_default()
+        };
+
+        Self {
+            window_secs,
+            burned,
+            tax_burned,
+            tax_collected,
+            burned_per_hour,
+        }
+    }
+}
+
+/// Token flows of a single address over a time window.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AddressFlows {
+    /// The address.
+    pub address: EthAddress,
+    /// Window length in seconds.
+    pub window_secs: u64,
+    /// DATA received in the window.
+    pub inflow: TokenAmount,
+    /// DATA sent in the window.
+    pub outflow: TokenAmount,
+    /// Transfers received in the window.
+    pub transfers_in: u64,
+    /// Transfers sent in the window.
+    pub transfers_out: u64,
+}
+
+impl AddressFlows {
+    /// Amount by which inflow exceeds outflow (zero if it does not).
+    #[must_use]
+    pub fn net_inflow(&self) -> TokenAmount {
+        self.inflow.saturating_sub(&self.outflow)
+    }
+
+    /// Amount by which outflow exceeds inflow (zero if it does not).
+    #[must_use]
+    pub fn net_outflow(&self) -> TokenAmount {
+        self.outflow.saturating_sub(&self.inflow)
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// HOLDER BALANCES
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// DATA balance of an address, derived from its transfers.
+///
+/// Balances are kept in wei, as `balanceOf` returns them. A debit larger than
+/// the balance means a transfer was missed: the balance is clamped to zero
+/// and the holder flagged for reconciliation against the chain.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HolderBalance {
+    /// The address.
+    pub address: EthAddress,
+    /// Balance in wei.
+    pub balance: U256,
+    /// Timestamp of the first transfer seen.
+    pub first_seen: DateTime<Utc>,
+    /// Timestamp of the last transfer applied.
+    pub last_active: DateTime<Utc>,
+    /// Block of the last transfer applied.
+    pub last_block: BlockNumber,
+    /// Log index of the last transfer applied.
+    pub last_log_index: u64,
+    /// Whether the balance is known to be off, and should be read from the
+    /// chain.
+    pub needs_reconciliation: bool,
+    /// When the balance was last compared with the chain.
+    pub reconciled_at: Option<DateTime<Utc>>,
+}
+
+impl HolderBalance {
+    /// Address first seen at `at`, holding nothing yet.
+    #[must_use]
+    pub const fn new(address: EthAddress, at: DateTime<Utc>) -> Self {
+        Self {
+            address,
+            balance: U256::ZERO,
+            first_seen: at,
+            last_active: at,
+            last_block: BlockNumber::new(0),
+            last_log_index: 0,
+            needs_reconciliation: false,
+            reconciled_at: None,
+        }
+    }
+
+    /// Check if the address holds any DATA.
+    #[must_use]
+    pub fn is_holder(&self) -> bool {
+        !self.balance.is_zero()
+    }
+
+    /// Check if a transfer at `log_index` of `block` comes after the last one
+    /// applied, so replayed transfers are not applied twice.
+    #[must_use]
+    pub fn applies(&self, block: BlockNumber, log_index: u64) -> bool {
+        (block, log_index) > (self.last_block, self.last_log_index)
+    }
+
+    /// Add `amount` to the balance.
+    ///
+    /// A balance that would overflow is capped and flagged.
+    pub const fn credit(&mut self, amount: U256) {
+        if let Some(balance) = self.balance.checked_add(amount) {
+            self.balance = balance;
+        } else {
+            self.balance = U256::MAX;
+            self.needs_reconciliation = true;
+        }
+    }
+
+    /// Subtract `amount` from the balance.
+    ///
+    /// A balance that would go negative is clamped to zero and flagged; the
+    /// amount missing is returned.
+    pub fn debit(&mut self, amount: U256) -> Option<U256> {
+        if let Some(balance) = self.balance.checked_sub(amount) {
+            self.balance = balance;
+            return None;
+        }
+        let shortfall = amount - self.balance;
+        self.balance = U256::ZERO;
+        self.needs_reconciliation = true;
+        Some(shortfall)
+    }
+
+    /// Record that the transfer at `log_index` of `block`, at `at`, has been
+    /// applied.
+    pub const fn mark_applied(&mut self, block: BlockNumber, log_index: u64, at: DateTime<Utc>) {
+        self.last_block = block;
+        self.last_log_index = log_index;
+        self.last_active = at;
+    }
+}
+
+/// Order of a holder listing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HolderSort {
+    /// Largest balance first.
+    #[default]
+    Balance,
+    /// Newest holder first.
+    FirstSeen,
+    /// Most recently active first.
+    LastActive,
+}
+
+impl HolderSort {
+    /// All orders.
+    pub const ALL: [Self; 3] = [Self::Balance, Self::FirstSeen, Self::LastActive];
+
+    /// Name of the order in API queries.
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Balance => "balance",
+            Self::FirstSeen => "first_seen",
+            Self::LastActive => "last_active",
+        }
+    }
+}
+
+impl std::fmt::Display for HolderSort {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for HolderSort {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|sort| sort.as_str() == s)
+            .ok_or_else(|| {
+                format!("Unknown sort: {s} (expected balance, first_seen or last_active)")
+            })
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// EVENT OUTBOX
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Event waiting in the outbox to be published to the streaming system.
+///
+/// Written in the same transaction as the state change the event describes,
+/// so exactly the committed events are published, see
+/// [`EventOutboxStore`](crate::ports::EventOutboxStore).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OutboxEvent {
+    /// Topic the event is published to.
+    pub topic: String,
+    /// Entity the event belongs to, see [`GhostnetEvent::aggregate`].
+    pub aggregate: String,
+    /// Event type name, e.g. `JackedIn`.
+    pub event_type: String,
+    /// Serialized event, encoded in the wire schemas when published.
+    pub payload: Vec<u8>,
+    /// Block the event was emitted in.
+    pub block_number: BlockNumber,
+}
+
+impl OutboxEvent {
+    /// Outbox entry publishing `event` to `topic`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the event cannot be serialized.
+    pub fn new(topic: &str, event: &GhostnetEvent) -> serde_json::Result<Self> {
+        Ok(Self {
+            topic: topic.to_string(),
+            aggregate: event.aggregate(),
+            event_type: event.type_name().to_string(),
+            payload: serde_json::to_vec(event)?,
+            block_number: BlockNumber::new(event.metadata().block_number),
+        })
+    }
+}
+
+/// Outbox entry as stored, awaiting publication.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutboxRecord {
+    /// Position in the outbox; entries are published in ID order.
+    pub id: i64,
+    /// The event.
+    pub event: OutboxEvent,
+    /// Failed publish attempts so far.
+    pub attempts: u32,
+    /// When the entry was written.
+    pub created_at: DateTime<Utc>,
+}
+
+/// Published event kept in the event log for replay.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoggedEvent {
+    /// Topic the event was published to.
+    pub topic: String,
+    /// Position of the event within its topic, starting at 1.
+    pub sequence: u64,
+    /// Serialized event, as published.
+    pub payload: Vec<u8>,
+    /// When the streaming system accepted the event.
+    pub published_at: DateTime<Utc>,
+}
+
+/// Sequences of a topic's event log.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EventLogBounds {
+    /// Oldest sequence still kept, if any event is.
+    pub oldest: Option<u64>,
+    /// Latest published sequence, 0 before the first event.
+    pub latest: u64,
+}
+
+impl EventLogBounds {
+    /// Whether every published event from `from_sequence` on is still kept.
+    #[must_use]
+    pub fn retains(&self, from_sequence: u64) -> bool {
+        from_sequence > self.latest || self.oldest.is_some_and(|oldest| oldest <= from_sequence)
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// RAW LOGS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Log as received from RPC, archived before it is decoded.
+///
+/// Holds everything the router needs to handle the log again: the log itself
+/// and its [`EventMetadata`], including the transaction context if it was
+/// resolved.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-pub struct GlobalStats {
-    /// Total DATA locked across all levels.
-    pub total_value_locked: TokenAmount,
-    /// Total active positions.
-    pub total_positions: u32,
-    /// Total deaths ever.
-    pub total_deaths: u32,
-    /// Total DATA burned.
-    pub total_burned: TokenAmount,
-    /// Total emissions distributed.
-    pub total_emissions_distributed: TokenAmount,
-    /// Total toll collected (ETH).
-    pub total_toll_collected: TokenAmount,
-    /// Total DATA burned via buyback.
-    pub total_buyback_burned: TokenAmount,
-    /// Number of system resets.
-    pub system_reset_count: u32,
-    /// Last update time.
-    pub updated_at: DateTime<Utc>,
+pub struct RawLog {
+    /// Contract that emitted the log.
+    pub address: Address,
+    /// Chain the log was emitted on.
+    #[serde(default)]
+    pub chain_id: u64,
+    /// Topics; the first is the event signature hash.
+    pub topics: Vec<B256>,
+    /// ABI-encoded non-indexed fields.
+    pub data: Bytes,
+    /// Block containing the log.
+    pub block_number: BlockNumber,
+    /// Hash of the block.
+    pub block_hash: B256,
+    /// Transaction that emitted the log.
+    pub tx_hash: B256,
+    /// Index of the transaction within the block.
+    pub tx_index: u64,
+    /// Index of the log.
+    pub log_index: u64,
+    /// Block timestamp.
+    pub timestamp: DateTime<Utc>,
+    /// GHOSTNET function called by the transaction, if resolved.
+    pub tx_function: Option<String>,
+    /// Sender of the transaction, if resolved.
+    pub tx_from: Option<Address>,
 }
 
-/// Leaderboard entry.
+impl RawLog {
+    /// Archive entry for `log`, received with `meta`.
+    #[must_use]
+    pub fn new(log: &Log, meta: &EventMetadata) -> Self {
+        Self {
+            address: log.address(),
+            chain_id: meta.chain_id,
+            topics: log.topics().to_vec(),
+            data: log.data().data.clone(),
+            block_number: BlockNumber::new(meta.block_number),
+            block_hash: meta.block_hash,
+            tx_hash: meta.tx_hash,
+            tx_index: meta.tx_index,
+            log_index: meta.log_index,
+            timestamp: meta.timestamp,
+            tx_function: meta.tx_function.clone(),
+            tx_from: meta.tx_from,
+        }
+    }
+
+    /// The log, as the RPC returned it.
+    #[must_use]
+    pub fn log(&self) -> Log {
+        Log {
+            inner: alloy::primitives::Log::new_unchecked(
+                self.address,
+                self.topics.clone(),
+                self.data.clone(),
+            ),
+            block_hash: Some(self.block_hash),
+            block_number: Some(self.block_number.value()),
+            block_timestamp: u64::try_from(self.timestamp.timestamp()).ok(),
+            transaction_hash: Some(self.tx_hash),
+            transaction_index: Some(self.tx_index),
+            log_index: Some(self.log_index),
+            removed: false,
+        }
+    }
+
+    /// Metadata the log was received with.
+    #[must_use]
+    pub fn metadata(&self) -> EventMetadata {
+        EventMetadata {
+            block_number: self.block_number.value(),
+            block_hash: self.block_hash,
+            tx_hash: self.tx_hash,
+            tx_index: self.tx_index,
+            log_index: self.log_index,
+            timestamp: self.timestamp,
+            contract: self.address,
+            chain_id: self.chain_id,
+            tx_function: self.tx_function.clone(),
+            tx_from: self.tx_from,
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// QUARANTINED EVENTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Log set aside after its handler failed on it.
+///
+/// Kept until `ghostnet-indexer quarantine retry` routes it again
+/// successfully, or until a reorg orphans its block.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-pub struct LeaderboardEntry {
-    /// Position on the leaderboard (1-indexed).
-    pub rank: u32,
-    /// User address.
-    pub user_address: EthAddress,
-    /// Score (interpretation depends on leaderboard type).
-    pub score: TokenAmount,
-    /// Additional metadata (JSON).
-    pub metadata: Option<serde_json::Value>,
+pub struct QuarantinedEvent {
+    /// Row ID, used to retry the event.
+    pub id: i64,
+    /// The log, as received.
+    pub log: RawLog,
+    /// Handler the log was routed to, as named by `EventKind::handler`.
+    pub handler: String,
+    /// First indexed topic, the aggregate strict ordering is kept for.
+    pub aggregate: Option<B256>,
+    /// Error of the last attempt to handle the log.
+    pub error: String,
+    /// Earlier quarantined event of the same aggregate this one waits for.
+    pub blocked_by: Option<i64>,
+    /// Retries attempted since the event was quarantined.
+    pub attempts: u32,
+    /// When the event was quarantined.
+    pub quarantined_at: DateTime<Utc>,
+    /// When the event was last retried.
+    pub last_attempt_at: Option<DateTime<Utc>>,
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -452,6 +2350,7 @@ mod tests {
                 user_address: sample_address(),
                 level: Level::Subnet,
                 amount: TokenAmount::parse("100").unwrap(),
+                effective_stake: TokenAmount::parse("100").unwrap(),
                 reward_debt: TokenAmount::zero(),
                 entry_timestamp: Utc::now(),
                 last_add_timestamp: None,
@@ -460,6 +2359,8 @@ mod tests {
                 is_extracted: false,
                 exit_reason: None,
                 exit_timestamp: None,
+                survival_seconds: None,
+                scans_survived: None,
                 extracted_amount: None,
                 extracted_rewards: None,
                 created_at_block: BlockNumber::new(1000),
@@ -476,6 +2377,7 @@ mod tests {
                 user_address: sample_address(),
                 level: Level::Darknet,
                 amount: TokenAmount::zero(),
+                effective_stake: TokenAmount::zero(),
                 reward_debt: TokenAmount::zero(),
                 entry_timestamp: Utc::now(),
                 last_add_timestamp: None,
@@ -484,6 +2386,8 @@ mod tests {
                 is_extracted: false,
                 exit_reason: Some(ExitReason::Traced),
                 exit_timestamp: Some(Utc::now()),
+                survival_seconds: None,
+                scans_survived: None,
                 extracted_amount: None,
                 extracted_rewards: None,
                 created_at_block: BlockNumber::new(1000),
@@ -492,6 +2396,118 @@ mod tests {
 
             assert!(!pos.is_active());
         }
+
+        #[test]
+        fn close_records_survival() {
+            let entered = Utc::now();
+            let mut pos = Position {
+                id: Uuid::new_v4(),
+                user_address: sample_address(),
+                level: Level::Subnet,
+                amount: TokenAmount::parse("100").unwrap(),
+                effective_stake: TokenAmount::parse("100").unwrap(),
+                reward_debt: TokenAmount::zero(),
+                entry_timestamp: entered,
+                last_add_timestamp: None,
+                ghost_streak: GhostStreak::ZERO,
+                is_alive: true,
+                is_extracted: false,
+                exit_reason: None,
+                exit_timestamp: None,
+                survival_seconds: None,
+                scans_survived: None,
+                extracted_amount: None,
+                extracted_rewards: None,
+                created_at_block: BlockNumber::new(1000),
+                updated_at: entered,
+            };
+
+            let exited = entered + chrono::TimeDelta::hours(2);
+            pos.close(ExitReason::Culled, exited, 3);
+
+            assert!(!pos.is_active());
+            assert_eq!(pos.exit_reason, Some(ExitReason::Culled));
+            assert_eq!(pos.exit_timestamp, Some(exited));
+            assert_eq!(pos.survival_seconds, Some(7200));
+            assert_eq!(pos.scans_survived, Some(3));
+
+            let delta = LevelStatsDelta::culled(&pos);
+            assert_eq!(delta.culled_delta, Some(1));
+            assert_eq!(delta.survival_seconds_delta, Some(7200));
+            assert_eq!(delta.survival_samples_delta, Some(1));
+        }
+
+        #[test]
+        fn unknown_entry_has_no_survival() {
+            let exited = Utc::now();
+            let pos = Position::unknown_entry(
+                sample_address(),
+                TokenAmount::parse("5").unwrap(),
+                ExitReason::Extracted,
+                exited,
+                BlockNumber::new(42),
+            );
+
+            assert!(!pos.is_active());
+            assert!(pos.is_extracted);
+            assert_eq!(pos.level, Level::None);
+            assert_eq!(pos.entry_timestamp, exited);
+            assert_eq!(pos.survival_seconds, None);
+            assert_eq!(pos.scans_survived, None);
+
+            let delta = LevelStatsDelta::closed(&pos);
+            assert_eq!(delta.survival_seconds_delta, None);
+            assert_eq!(delta.survival_samples_delta, None);
+        }
+
+        #[test]
+        fn listing_cursor_round_trips() {
+            let cursor = PositionCursor {
+                entry_timestamp: DateTime::from_timestamp_millis(1_700_000_000_123).unwrap(),
+                id: Uuid::new_v4(),
+            };
+
+            assert_eq!(cursor.to_string().parse::<PositionCursor>(), Ok(cursor));
+            assert!("1700000000123".parse::<PositionCursor>().is_err());
+            assert!("soon_0".parse::<PositionCursor>().is_err());
+
+            assert!(PositionFilter::default().is_unfiltered());
+            let next_page = PositionFilter {
+                after: Some(cursor),
+                ..PositionFilter::default()
+            };
+            assert!(!next_page.is_unfiltered());
+        }
+    }
+
+    mod level_stats_tests {
+        use super::*;
+
+        #[test]
+        fn survival_averages_and_cull_rate() {
+            let mut stats = LevelStats::empty(Level::Darknet, Utc::now());
+            assert_eq!(stats.average_survival_seconds(), None);
+            assert!(stats.cull_rate().abs() < f64::EPSILON);
+
+            let mut delta = LevelStatsDelta {
+                culled_delta: Some(1),
+                survival_seconds_delta: Some(100),
+                survival_samples_delta: Some(1),
+                ..LevelStatsDelta::default()
+            };
+            delta.merge(LevelStatsDelta {
+                extracted_delta: Some(1),
+                survival_seconds_delta: Some(300),
+                survival_samples_delta: Some(1),
+                ..LevelStatsDelta::default()
+            });
+            stats.apply(&delta, Utc::now());
+            stats.total_deaths = 2;
+
+            assert_eq!(stats.total_culled, 1);
+            assert_eq!(stats.average_survival_seconds(), Some(200.0));
+            assert!((stats.cull_rate() - 0.25).abs() < f64::EPSILON);
+        }
     }
 
     mod round_tests {
@@ -512,10 +2528,121 @@ mod tests {
                 outcome: None,
                 resolve_time: None,
                 total_burned: None,
+                payout_multiple_bps: None,
+                house_take: None,
             };
 
             assert_eq!(round.total_pot().to_string(), "800");
         }
+
+        fn pools(over: &str, under: &str) -> Pools {
+            Pools {
+                over_pool: TokenAmount::parse(over).unwrap(),
+                under_pool: TokenAmount::parse(under).unwrap(),
+            }
+        }
+
+        #[test]
+        fn odds_follow_the_pools() {
+            // Nothing bet yet: no probability, no payout
+            let empty = Pools::default();
+            assert_eq!(empty.implied_probability_bps(true), None);
+            assert_eq!(empty.payout_multiple_bps(true), None);
+
+            // A lone bet gets its own stake back less the rake
+            let first = empty.with_bet(true, &TokenAmount::parse("100").unwrap());
+            assert_eq!(first.implied_probability_bps(true), Some(10_000));
+            assert_eq!(first.implied_probability_bps(false), Some(0));
+            assert_eq!(first.payout_multiple_bps(true), Some(9_500));
+            assert_eq!(first.payout_multiple_bps(false), None);
+
+            // 300 on OVER, 100 on UNDER: 380 after rake
+            let pools = pools("300", "100");
+            assert_eq!(pools.implied_probability_bps(true), Some(7_500));
+            assert_eq!(pools.implied_probability_bps(false), Some(2_500));
+            assert_eq!(pools.payout_multiple_bps(true), Some(12_666));
+            assert_eq!(pools.payout_multiple_bps(false), Some(38_000));
+        }
+
+        #[test]
+        fn settlement_pays_the_winning_pool() {
+            let round = Round {
+                id: Uuid::new_v4(),
+                round_id: "1".into(),
+                round_type: RoundType::DeathCount,
+                target_level: None,
+                line: TokenAmount::parse("10").unwrap(),
+                deadline: Utc::now(),
+                over_pool: TokenAmount::parse("300").unwrap(),
+                under_pool: TokenAmount::zero(),
+                is_resolved: false,
+                outcome: None,
+                resolve_time: None,
+                total_burned: None,
+                payout_multiple_bps: None,
+                house_take: None,
+            };
+            let pot = TokenAmount::parse("300").unwrap();
+            let burned = TokenAmount::parse("15").unwrap();
+
+            let over = round.settle(true, &pot, &burned, Utc::now());
+            assert_eq!(over.payout_multiple_bps, Some(9_500));
+            assert_eq!(over.house_take, burned);
+
+            // Nobody can claim a pot nobody bet on the outcome of
+            let under = round.settle(false, &pot, &burned, Utc::now());
+            assert_eq!(under.payout_multiple_bps, None);
+            assert_eq!(under.house_take, pot);
+        }
+
+        #[test]
+        fn odds_history_replays_bets_in_chain_order() {
+            let bet = |block: u64, log_index: u64, is_over: bool, amount: &str| Bet {
+                id: Uuid::new_v4(),
+                round_id: Uuid::nil(),
+                user_address: sample_address(),
+                amount: TokenAmount::parse(amount).unwrap(),
+                is_over,
+                block_number: BlockNumber::new(block),
+                log_index,
+                placed_at: Utc::now(),
+                pools_before: Pools::default(),
+                implied_probability_bps: None,
+                payout_multiple_bps: None,
+                is_claimed: false,
+                winnings: None,
+                claimed_at: None,
+            };
+            // Stored out of order; the second bet of block 7 came first
+            let bets = [
+                bet(7, 4, false, "100"),
+                bet(8, 0, true, "200"),
+                bet(7, 1, true, "100"),
+            ];
+
+            let history = OddsPoint::history(&bets);
+            let points: Vec<_> = history
+                .iter()
+                .map(|point| {
+                    (
+                        point.block_number.value(),
+                        point.log_index,
+                        point.over_probability_bps,
+                        point.over_payout_multiple_bps,
+                    )
+                })
+                .collect();
+            assert_eq!(
+                points,
+                [
+                    (7, 1, Some(10_000), Some(9_500)),
+                    (7, 4, Some(5_000), Some(19_000)),
+                    (8, 0, Some(7_500), Some(12_666)),
+                ]
+            );
+            assert_eq!(history[2].pools, pools("300", "100"));
+            assert_eq!(history[2].under_payout_multiple_bps, Some(38_000));
+        }
     }
 
     mod bet_tests {
@@ -529,6 +2656,12 @@ mod tests {
                 user_address: sample_address(),
                 amount: TokenAmount::parse("100").unwrap(),
                 is_over: true,
+                block_number: BlockNumber::new(1),
+                log_index: 0,
+                placed_at: Utc::now(),
+                pools_before: Pools::default(),
+                implied_probability_bps: None,
+                payout_multiple_bps: Some(9_500),
                 is_claimed: false,
                 winnings: None,
                 claimed_at: None,
@@ -547,50 +2680,415 @@ mod tests {
         use super::*;
         use chrono::Duration;
 
-        #[test]
-        fn boost_active_before_expiry() {
-            let now = Utc::now();
-            let boost = Boost {
+        fn boost(expiry: DateTime<Utc>) -> Boost {
+            Boost {
                 id: Uuid::new_v4(),
+                position_id: Uuid::new_v4(),
                 user_address: sample_address(),
                 boost_type: BoostType::DeathReduction,
-                value_bps: 3500,
-                expiry: now + Duration::hours(1),
-                created_at: now,
-            };
+                value_bps: 3500, // 35%
+                expiry,
+                block_number: BlockNumber::new(1000),
+                log_index: 0,
+                created_at: expiry - Duration::hours(2),
+                expired_at: None,
+            }
+        }
+
+        #[test]
+        fn boost_active_before_expiry() {
+            let now = Utc::now();
+            let boost = boost(now + Duration::hours(1));
 
             assert!(boost.is_active(now));
+            assert_eq!(boost.remaining(now), Duration::hours(1));
         }
 
         #[test]
         fn boost_inactive_after_expiry() {
             let now = Utc::now();
-            let boost = Boost {
-                id: Uuid::new_v4(),
-                user_address: sample_address(),
-                boost_type: BoostType::DeathReduction,
-                value_bps: 3500,
-                expiry: now - Duration::hours(1),
-                created_at: now - Duration::hours(2),
-            };
+            let boost = boost(now - Duration::hours(1));
+
+            assert!(!boost.is_active(now));
+            assert_eq!(boost.remaining(now), Duration::zero());
+        }
+
+        #[test]
+        fn expired_boost_is_inactive_before_expiry() {
+            let now = Utc::now();
+            let mut boost = boost(now + Duration::hours(1));
+            boost.expired_at = Some(now);
 
             assert!(!boost.is_active(now));
+            assert_eq!(boost.remaining(now), Duration::zero());
         }
 
         #[test]
         fn boost_multiplier_calculation() {
-            let boost = Boost {
-                id: Uuid::new_v4(),
-                user_address: sample_address(),
-                boost_type: BoostType::DeathReduction,
-                value_bps: 3500, // 35%
-                expiry: Utc::now(),
-                created_at: Utc::now(),
-            };
+            let boost = boost(Utc::now());
 
             let expected = 0.35_f64;
             let actual = boost.multiplier();
             assert!((actual - expected).abs() < f64::EPSILON);
         }
+
+        #[test]
+        fn yield_multipliers_stack_additively_into_the_effective_stake() {
+            let now = Utc::now();
+            let multiplier = |value_bps| Boost {
+                boost_type: BoostType::YieldMultiplier,
+                value_bps,
+                ..boost(now + Duration::hours(1))
+            };
+            let mut position = Position::unknown_entry(
+                sample_address(),
+                TokenAmount::parse("100").unwrap(),
+                ExitReason::Extracted,
+                now,
+                BlockNumber::new(1),
+            );
+            assert_eq!(position.effective_stake, position.amount);
+
+            // Death reductions do not weight the stake
+            position.apply_boosts(&[boost(now + Duration::hours(1))]);
+            assert_eq!(position.effective_stake.to_string(), "100");
+
+            position.apply_boosts(&[multiplier(2000)]);
+            assert_eq!(position.effective_stake.to_string(), "120");
+
+            // Summed, not compounded: 1.5x rather than 1.2 * 1.3 = 1.56x
+            let stacked = [multiplier(2000), boost(now), multiplier(3000)];
+            position.apply_boosts(&stacked);
+            assert_eq!(position.effective_stake.to_string(), "150");
+
+            // A stake change is weighted by the boosts still active
+            position.amount = TokenAmount::parse("200").unwrap();
+            position.apply_boosts(&stacked);
+            assert_eq!(position.effective_stake.to_string(), "300");
+
+            // Once they lapse the stake counts as it is
+            position.apply_boosts(&[]);
+            assert_eq!(position.effective_stake, position.amount);
+
+            // Rounded half up to whole wei
+            position.amount = TokenAmount::from_wei(U256::from(3_u64), 18);
+            position.apply_boosts(&[multiplier(5000)]);
+            assert_eq!(
+                position.effective_stake,
+                TokenAmount::from_wei(U256::from(5_u64), 18)
+            );
+        }
+
+        #[test]
+        fn applied_boost_takes_expiry_from_event() {
+            let position = Position::unknown_entry(
+                sample_address(),
+                TokenAmount::zero(),
+                ExitReason::Extracted,
+                Utc::now(),
+                BlockNumber::new(1),
+            );
+            let mut event = BoostAppliedEvent {
+                meta: EventMetadata {
+                    block_number: 1200,
+                    block_hash: B256::ZERO,
+                    tx_hash: B256::ZERO,
+                    tx_index: 0,
+                    log_index: 3,
+                    timestamp: Utc::now(),
+                    contract: Address::ZERO,
+                    chain_id: 6343,
+                    tx_function: None,
+                    tx_from: None,
+                },
+                user: Address::ZERO,
+                boost_type: BoostType::YieldMultiplier,
+                value_bps: 500,
+                expiry: 1_767_225_600,
+            };
+
+            let boost = Boost::applied(&position, &event);
+            assert_eq!(boost.position_id, position.id);
+            assert_eq!(boost.expiry.to_rfc3339(), "2026-01-01T00:00:00+00:00");
+            assert_eq!((boost.block_number.value(), boost.log_index), (1200, 3));
+            assert_eq!(boost.value_bps, 500);
+
+            event.expiry = u64::MAX;
+            assert_eq!(
+                Boost::applied(&position, &event).expiry,
+                DateTime::<Utc>::MAX_UTC
+            );
+        }
+
+        #[test]
+        fn lapsed_boost_ends_at_the_earlier_of_expiry_and_exit() {
+            let expiry = Utc::now();
+            let lapsed = LapsedBoost {
+                boost: boost(expiry),
+                position_closed_at: None,
+            };
+            assert_eq!(lapsed.ended_at(), expiry);
+            assert!(!lapsed.cut_short());
+
+            let closed = expiry - Duration::minutes(10);
+            let lapsed = LapsedBoost {
+                position_closed_at: Some(closed),
+                ..lapsed
+            };
+            assert_eq!(lapsed.ended_at(), closed);
+            assert!(lapsed.cut_short());
+
+            // Closing after the boost lapsed does not cut it short
+            let lapsed = LapsedBoost {
+                position_closed_at: Some(expiry + Duration::minutes(10)),
+                ..lapsed
+            };
+            assert_eq!(lapsed.ended_at(), expiry);
+            assert!(!lapsed.cut_short());
+        }
+    }
+
+    mod history_tests {
+        use super::*;
+
+        #[test]
+        fn action_names_round_trip() {
+            for action in [
+                PositionAction::JackedIn,
+                PositionAction::StakeAdded,
+                PositionAction::Extracted,
+                PositionAction::Traced,
+                PositionAction::Culled,
+                PositionAction::BoostApplied,
+                PositionAction::SystemReset,
+                PositionAction::RewardsClaimed,
+                PositionAction::Superseded,
+            ] {
+                assert_eq!(action.name().parse::<PositionAction>(), Ok(action));
+            }
+            assert!("Exploded".parse::<PositionAction>().is_err());
+        }
+
+        #[test]
+        fn cursor_round_trips() {
+            let cursor = HistoryCursor {
+                timestamp: DateTime::from_timestamp_millis(1_700_000_000_123).unwrap(),
+                id: Uuid::now_v7(),
+            };
+
+            assert_eq!(cursor.to_string().parse::<HistoryCursor>(), Ok(cursor));
+            assert!("1700000000123".parse::<HistoryCursor>().is_err());
+            assert!("soon_0".parse::<HistoryCursor>().is_err());
+        }
+    }
+
+    mod level_history_tests {
+        use super::*;
+
+        fn at(day: i64, hour: i64, minute: i64) -> DateTime<Utc> {
+            DateTime::from_timestamp(1_700_006_400, 0).unwrap()
+                + TimeDelta::days(day)
+                + TimeDelta::hours(hour)
+                + TimeDelta::minutes(minute)
+        }
+
+        fn scan(at: DateTime<Utc>, deaths: u32, survivors: u32) -> LevelScanStats {
+            LevelScanStats {
+                scan_id: at.timestamp().to_string(),
+                level: Level::Darknet,
+                scanned_at: at,
+                block_number: None,
+                deaths,
+                survivors,
+                total_staked: TokenAmount::parse(&(survivors * 10).to_string()).unwrap(),
+            }
+        }
+
+        #[test]
+        fn bucket_names_round_trip() {
+            for bucket in HistoryBucket::ALL {
+                assert_eq!(bucket.as_str().parse::<HistoryBucket>(), Ok(bucket));
+                assert_eq!(
+                    serde_json::to_value(bucket).unwrap(),
+                    serde_json::json!(bucket.as_str())
+                );
+            }
+            assert!("15m".parse::<HistoryBucket>().is_err());
+        }
+
+        #[test]
+        fn buckets_align_to_the_epoch() {
+            assert_eq!(at(0, 0, 0).timestamp() % 86_400, 0);
+            assert_eq!(HistoryBucket::Day.start_of(at(2, 13, 47)), at(2, 0, 0));
+            assert_eq!(HistoryBucket::Hour.start_of(at(2, 13, 47)), at(2, 13, 0));
+            assert_eq!(HistoryBucket::FiveMinutes.start_of(at(2, 13, 47)), at(2, 13, 45));
+        }
+
+        #[test]
+        fn daily_series_pools_scans_and_fills_empty_days() {
+            let scans = [
+                scan(at(0, 2, 0), 3, 7),
+                scan(at(0, 20, 0), 1, 9),
+                // Nothing on day 1
+                scan(at(2, 5, 0), 0, 12),
+                // Outside the range
+                scan(at(4, 1, 0), 5, 5),
+            ];
+
+            let series =
+                LevelHistoryPoint::series(&scans, at(0, 0, 0), at(4, 0, 0), HistoryBucket::Day);
+            let days: Vec<_> = series.iter().map(|point| point.timestamp).collect();
+            assert_eq!(days, [at(0, 0, 0), at(1, 0, 0), at(2, 0, 0), at(3, 0, 0)]);
+
+            // 4 of the 20 positions scanned on day 0 died
+            assert_eq!((series[0].scans, series[0].deaths), (2, 4));
+            assert!((series[0].death_rate.unwrap() - 0.2).abs() < f64::EPSILON);
+            assert_eq!(series[0].survivor_count, Some(9));
+            assert_eq!(series[0].tvl, Some(TokenAmount::parse("90").unwrap()));
+
+            assert_eq!((series[1].scans, series[1].deaths, series[1].death_rate), (0, 0, None));
+            assert_eq!(series[1].survivor_count, Some(9));
+            assert_eq!(series[1].tvl, series[0].tvl);
+
+            assert_eq!(series[2].death_rate, Some(0.0));
+            assert_eq!(series[3].survivor_count, Some(12));
+            assert_eq!(series[3].tvl, Some(TokenAmount::parse("120").unwrap()));
+        }
+
+        #[test]
+        fn series_starts_empty_before_the_first_scan() {
+            let scans = [scan(at(1, 0, 30), 1, 3)];
+
+            let series =
+                LevelHistoryPoint::series(&scans, at(0, 23, 0), at(1, 1, 0), HistoryBucket::Hour);
+            assert_eq!(series.len(), 2);
+            assert_eq!((series[0].survivor_count, series[0].tvl.clone()), (None, None));
+            assert_eq!(series[1].death_rate, Some(0.25));
+
+            // A partial bucket at the start counts only the scans in range
+            let series = LevelHistoryPoint::series(
+                &scans,
+                at(1, 0, 31),
+                at(1, 0, 40),
+                HistoryBucket::FiveMinutes,
+            );
+            assert_eq!(series.len(), 2);
+            assert_eq!(series[0].timestamp, at(1, 0, 30));
+            assert!(series.iter().all(|point| point.scans == 0));
+        }
+
+        #[test]
+        fn an_empty_level_has_no_death_rate() {
+            let series = LevelHistoryPoint::series(
+                &[scan(at(0, 0, 0), 0, 0)],
+                at(0, 0, 0),
+                at(0, 1, 0),
+                HistoryBucket::Hour,
+            );
+            assert_eq!(series[0].scans, 1);
+            assert_eq!(series[0].death_rate, None);
+            assert_eq!(series[0].survivor_count, Some(0));
+        }
+    }
+
+    mod holder_tests {
+        use super::*;
+
+        fn holder() -> HolderBalance {
+            HolderBalance::new(sample_address(), Utc::now())
+        }
+
+        #[test]
+        fn mint_transfer_and_burn_sequence() {
+            let mut holder = holder();
+
+            holder.credit(U256::from(100));
+            assert_eq!(holder.debit(U256::from(30)), None);
+            holder.credit(U256::from(5));
+            assert_eq!(holder.debit(U256::from(75)), None);
+
+            assert_eq!(holder.balance, U256::ZERO);
+            assert!(!holder.is_holder());
+            assert!(!holder.needs_reconciliation);
+        }
+
+        #[test]
+        fn underflow_clamps_and_flags() {
+            let mut holder = holder();
+            holder.credit(U256::from(10));
+
+            assert_eq!(holder.debit(U256::from(25)), Some(U256::from(15)));
+            assert_eq!(holder.balance, U256::ZERO);
+            assert!(holder.needs_reconciliation);
+        }
+
+        #[test]
+        fn overflow_caps_and_flags() {
+            let mut holder = holder();
+            holder.credit(U256::MAX);
+            holder.credit(U256::from(1));
+
+            assert_eq!(holder.balance, U256::MAX);
+            assert!(holder.needs_reconciliation);
+        }
+
+        #[test]
+        fn transfers_apply_once_in_chain_order() {
+            let mut holder = holder();
+            assert!(holder.applies(BlockNumber::new(10), 0));
+
+            holder.mark_applied(BlockNumber::new(10), 4, Utc::now());
+            assert!(!holder.applies(BlockNumber::new(10), 4));
+            assert!(!holder.applies(BlockNumber::new(9), 7));
+            assert!(holder.applies(BlockNumber::new(10), 5));
+            assert!(holder.applies(BlockNumber::new(11), 0));
+        }
+
+        #[test]
+        fn sort_names_round_trip() {
+            for sort in HolderSort::ALL {
+                assert_eq!(sort.as_str().parse::<HolderSort>(), Ok(sort));
+            }
+            assert!("richest".parse::<HolderSort>().is_err());
+        }
+    }
+
+    mod raw_log_tests {
+        use super::*;
+
+        #[test]
+        fn log_and_metadata_round_trip() {
+            let meta = EventMetadata {
+                block_number: 42,
+                block_hash: B256::repeat_byte(0x42),
+                tx_hash: B256::repeat_byte(0x11),
+                tx_index: 3,
+                log_index: 7,
+                timestamp: DateTime::from_timestamp(1_700_000_000, 0).unwrap(),
+                contract: Address::repeat_byte(0xc0),
+                chain_id: 6343,
+                tx_function: Some("jackIn".into()),
+                tx_from: Some(Address::repeat_byte(0x01)),
+            };
+            let log = Log {
+                inner: alloy::primitives::Log::new_unchecked(
+                    meta.contract,
+                    vec![B256::repeat_byte(0xaa), B256::repeat_byte(0xbb)],
+                    Bytes::from_static(&[1, 2, 3]),
+                ),
+                block_hash: Some(meta.block_hash),
+                block_number: Some(meta.block_number),
+                block_timestamp: Some(1_700_000_000),
+                transaction_hash: Some(meta.tx_hash),
+                transaction_index: Some(meta.tx_index),
+                log_index: Some(meta.log_index),
+                removed: false,
+            };
+
+            let raw = RawLog::new(&log, &meta);
+
+            assert_eq!(raw.log(), log);
+            assert_eq!(raw.metadata(), meta);
+        }
     }
 }
@@ -142,6 +142,26 @@ impl From<Level> for i16 {
     }
 }
 
+/// Parses a valid level from its number (`"5"`) or its serialized name
+/// (`"BlackIce"`).
+impl std::str::FromStr for Level {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let level = match s {
+            "Vault" => Some(Self::Vault),
+            "Mainframe" => Some(Self::Mainframe),
+            "Subnet" => Some(Self::Subnet),
+            "Darknet" => Some(Self::Darknet),
+            "BlackIce" => Some(Self::BlackIce),
+            _ => s.parse::<u8>().ok().and_then(|n| Self::try_from(n).ok()),
+        };
+        level
+            .filter(|level| *level != Self::None)
+            .ok_or_else(|| format!("Unknown level: {s}"))
+    }
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // BOOST TYPE - Types of boosts that can be applied to positions
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -150,6 +170,18 @@ impl From<Level> for i16 {
 ///
 /// Boosts are earned through mini-games (Trace Evasion, Hack Runs) and
 /// modify position parameters for a limited time.
+///
+/// # Solidity Mapping
+/// ```solidity
+/// enum BoostType {
+///     DEATH_REDUCTION,  // 0 - Reduces effective death rate
+///     YIELD_MULTIPLIER  // 1 - Multiplies reward earnings
+/// }
+/// ```
+///
+/// Boosts of the same type stack additively: `GhostCore` sums the
+/// `valueBps` of active death reductions, and the indexer sums those of
+/// yield multipliers the same way for a position's effective stake.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Type)]
 #[repr(i16)]
 #[sqlx(type_name = "smallint")]
@@ -176,6 +208,16 @@ impl BoostType {
     pub const fn all() -> [Self; 2] {
         [Self::DeathReduction, Self::YieldMultiplier]
     }
+
+    /// Whether the boost weights the position's effective stake.
+    ///
+    /// A yield multiplier of `value_bps` adds that many basis points to the
+    /// weight of the stake (`5000` makes it count 1.5 times). A death
+    /// reduction lowers the death rate and leaves the stake as it is.
+    #[must_use]
+    pub const fn weights_stake(&self) -> bool {
+        matches!(self, Self::YieldMultiplier)
+    }
 }
 
 /// Error returned when an invalid boost type value is provided.
@@ -355,6 +397,72 @@ impl std::str::FromStr for ExitReason {
     }
 }
 
+// ═══════════════════════════════════════════════════════════════════════════════
+// LEADERBOARD TYPE - Pre-computed rankings
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Leaderboards maintained by the indexer.
+///
+/// The snake-case name doubles as the cache key and the API path segment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LeaderboardType {
+    /// Highest ghost streak among alive positions.
+    GhostStreak,
+    /// Most DATA extracted across all of a wallet's positions.
+    TotalExtracted,
+    /// Largest single extraction per wallet.
+    BiggestExtraction,
+    /// Most DATA won from `DeadPool` bets.
+    DeadPoolWinners,
+    /// Most DATA earned from cascades of other players' deaths.
+    CascadeEarnings,
+    /// Highest boost-weighted stake among alive positions.
+    EffectiveStake,
+}
+
+impl LeaderboardType {
+    /// All leaderboard types, in display order.
+    pub const ALL: [Self; 6] = [
+        Self::GhostStreak,
+        Self::TotalExtracted,
+        Self::BiggestExtraction,
+        Self::DeadPoolWinners,
+        Self::CascadeEarnings,
+        Self::EffectiveStake,
+    ];
+
+    /// Stable identifier used for cache keys and API paths.
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::GhostStreak => "ghost_streak",
+            Self::TotalExtracted => "total_extracted",
+            Self::BiggestExtraction => "biggest_extraction",
+            Self::DeadPoolWinners => "dead_pool_winners",
+            Self::CascadeEarnings => "cascade_earnings",
+            Self::EffectiveStake => "effective_stake",
+        }
+    }
+}
+
+impl std::fmt::Display for LeaderboardType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for LeaderboardType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.as_str() == s)
+            .ok_or_else(|| format!("Unknown leaderboard type: {s}"))
+    }
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // TESTS
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -425,6 +533,18 @@ mod tests {
             }
         }
 
+        #[test]
+        fn parses_numbers_and_names() {
+            for level in Level::all_valid() {
+                assert_eq!(u8::from(level).to_string().parse::<Level>(), Ok(level));
+                let name = serde_json::to_value(level).unwrap();
+                assert_eq!(name.as_str().unwrap().parse::<Level>(), Ok(level));
+            }
+            assert!("0".parse::<Level>().is_err());
+            assert!("None".parse::<Level>().is_err());
+            assert!("Black Ice".parse::<Level>().is_err());
+        }
+
         #[test]
         fn vault_has_no_scans() {
             assert!(!Level::Vault.has_scans());
@@ -512,4 +632,28 @@ mod tests {
             assert!(!ExitReason::Superseded.is_loss());
         }
     }
+
+    mod leaderboard_type_tests {
+        use super::*;
+
+        #[test]
+        fn parse_roundtrip() {
+            for kind in LeaderboardType::ALL {
+                assert_eq!(kind.as_str().parse::<LeaderboardType>(), Ok(kind));
+            }
+        }
+
+        #[test]
+        fn parse_unknown() {
+            assert!("streak".parse::<LeaderboardType>().is_err());
+        }
+
+        #[test]
+        fn serde_matches_as_str() {
+            for kind in LeaderboardType::ALL {
+                let json = serde_json::to_string(&kind).unwrap();
+                assert_eq!(json, format!("\"{kind}\""));
+            }
+        }
+    }
 }
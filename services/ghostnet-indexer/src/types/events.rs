@@ -2,10 +2,13 @@
 //!
 //! Each struct corresponds to a Solidity event emitted by the contracts.
 //! Events are decoded from blockchain logs and enriched with metadata.
+//! [`DerivedEvent`]s are the exception: the indexer derives them from stored
+//! state, e.g. a round's deadline coming near.
 
 use alloy::primitives::{Address, B256, U256};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
 use super::enums::{BoostType, Level, RoundType};
 
@@ -32,6 +35,20 @@ pub struct EventMetadata {
     pub timestamp: DateTime<Utc>,
     /// Contract address that emitted this event.
     pub contract: Address,
+    /// ID of the chain the event was emitted on.
+    ///
+    /// Defaults to `0` for events serialized before multi-chain indexing.
+    #[serde(default)]
+    pub chain_id: u64,
+    /// GHOSTNET function called by the transaction, e.g. `GhostCore.jackIn`.
+    ///
+    /// Only resolved when transaction context is enabled; `None` otherwise or
+    /// when the transaction called an unknown function (e.g. a router).
+    #[serde(default)]
+    pub tx_function: Option<String>,
+    /// Sender of the transaction, when transaction context is enabled.
+    #[serde(default)]
+    pub tx_from: Option<Address>,
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -250,6 +267,36 @@ impl GhostnetEvent {
             Self::TokensClaimed(_) => "TeamVesting",
         }
     }
+
+    /// Key of the entity this event belongs to, e.g. `position:0x…`.
+    ///
+    /// Events of one aggregate must reach consumers in the order they were
+    /// emitted; events of different aggregates are independent. Position
+    /// events belong to the user, DeadPool events to their round and
+    /// TraceScan events to their scan. Level-wide events belong to the level,
+    /// and everything else to its contract.
+    #[must_use]
+    pub fn aggregate(&self) -> String {
+        match self {
+            Self::JackedIn(e) => format!("position:{:#x}", e.user),
+            Self::StakeAdded(e) => format!("position:{:#x}", e.user),
+            Self::Extracted(e) => format!("position:{:#x}", e.user),
+            Self::BoostApplied(e) => format!("position:{:#x}", e.user),
+            Self::PositionCulled(e) => format!("position:{:#x}", e.victim),
+            Self::ScanExecuted(e) => format!("scan:{}", e.scan_id),
+            Self::DeathsSubmitted(e) => format!("scan:{}", e.scan_id),
+            Self::ScanFinalized(e) => format!("scan:{}", e.scan_id),
+            Self::RoundCreated(e) => format!("round:{}", e.round_id),
+            Self::BetPlaced(e) => format!("round:{}", e.round_id),
+            Self::RoundResolved(e) => format!("round:{}", e.round_id),
+            Self::WinningsClaimed(e) => format!("round:{}", e.round_id),
+            Self::DeathsProcessed(e) => format!("level:{}", e.level.name()),
+            Self::SurvivorsUpdated(e) => format!("level:{}", e.level.name()),
+            Self::EmissionsAdded(e) => format!("level:{}", e.level.name()),
+            Self::CascadeDistributed(e) => format!("level:{}", e.source_level.name()),
+            _ => self.contract_name().to_string(),
+        }
+    }
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -643,6 +690,119 @@ pub struct TokensClaimedEvent {
     pub amount: U256,
 }
 
+// ═══════════════════════════════════════════════════════════════════════════════
+// DERIVED EVENTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// An event the indexer derives from stored state rather than decodes from a
+/// log, published to the `derived` topic.
+///
+/// Serialized with a type tag like [`GhostnetEvent`]:
+/// ```json
+/// {"RoundClosingSoon": {"round_id": "0x7", "threshold_secs": 600, ...}}
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DerivedEvent {
+    /// A round's betting deadline is near
+    RoundClosingSoon(RoundClosingSoonEvent),
+    /// A round's deadline passed without it being resolved
+    RoundAwaitingResolution(RoundAwaitingResolutionEvent),
+    /// A boost lapsed, or its position closed first
+    BoostExpired(BoostExpiredEvent),
+}
+
+impl DerivedEvent {
+    /// Get the event type name.
+    #[must_use]
+    pub const fn event_name(&self) -> &'static str {
+        match self {
+            Self::RoundClosingSoon(_) => "RoundClosingSoon",
+            Self::RoundAwaitingResolution(_) => "RoundAwaitingResolution",
+            Self::BoostExpired(_) => "BoostExpired",
+        }
+    }
+
+    /// Key of the entity this event belongs to, like that of its on-chain
+    /// events: `round:<id>` for rounds, `position:<user>` for boosts.
+    #[must_use]
+    pub fn aggregate(&self) -> String {
+        match self {
+            Self::RoundClosingSoon(e) => format!("round:{}", e.round_id),
+            Self::RoundAwaitingResolution(e) => format!("round:{}", e.round_id),
+            Self::BoostExpired(e) => format!("position:{:#x}", e.user),
+        }
+    }
+
+    /// Stable message ID of the event.
+    ///
+    /// The same round and threshold, or the same boost, always give the same
+    /// ID, so an event derived again (e.g. after a restart) is dropped as a
+    /// redelivery.
+    #[must_use]
+    pub fn message_id(&self) -> u128 {
+        let key = match self {
+            Self::RoundClosingSoon(e) => {
+                format!("{}:{}:{}", self.event_name(), e.round_id, e.threshold_secs)
+            }
+            Self::RoundAwaitingResolution(e) => format!("{}:{}", self.event_name(), e.round_id),
+            Self::BoostExpired(e) => format!("{}:{}", self.event_name(), e.boost_id),
+        };
+        let hash = alloy::primitives::keccak256(key.as_bytes());
+        let mut id = [0u8; 16];
+        id.copy_from_slice(&hash[..16]);
+        u128::from_be_bytes(id)
+    }
+}
+
+/// A round's betting deadline came within one of the configured thresholds.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoundClosingSoonEvent {
+    /// Which round.
+    pub round_id: U256,
+    /// Threshold that was crossed, in seconds before the deadline.
+    pub threshold_secs: u64,
+    /// Seconds left until the deadline when the event was derived.
+    pub seconds_remaining: u64,
+    /// Betting closes at.
+    pub deadline: DateTime<Utc>,
+    /// When the indexer derived the event.
+    pub derived_at: DateTime<Utc>,
+}
+
+/// A round's deadline passed and no `RoundResolved` was indexed yet.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoundAwaitingResolutionEvent {
+    /// Which round.
+    pub round_id: U256,
+    /// Betting closed at.
+    pub deadline: DateTime<Utc>,
+    /// Seconds past the deadline when the event was derived.
+    pub seconds_overdue: u64,
+    /// When the indexer derived the event.
+    pub derived_at: DateTime<Utc>,
+}
+
+/// A boost stopped applying: it expired, or its position closed first.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BoostExpiredEvent {
+    /// Which boost (the indexer's ID).
+    pub boost_id: Uuid,
+    /// User who had the boost.
+    pub user: Address,
+    /// Type of boost.
+    pub boost_type: BoostType,
+    /// Boost value in basis points.
+    pub value_bps: u16,
+    /// When the boost was set to expire.
+    pub expiry: DateTime<Utc>,
+    /// When the boost stopped applying.
+    pub ended_at: DateTime<Utc>,
+    /// Whether the position closed before the boost expired.
+    pub position_closed: bool,
+    /// When the indexer derived the event.
+    pub derived_at: DateTime<Utc>,
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // TESTS
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -660,6 +820,9 @@ mod tests {
             log_index: 0,
             timestamp: Utc::now(),
             contract: Address::ZERO,
+            chain_id: 6343,
+            tx_function: None,
+            tx_from: None,
         }
     }
 
@@ -689,4 +852,36 @@ mod tests {
 
         assert_eq!(event.metadata().block_number, meta.block_number);
     }
+
+    #[test]
+    fn ghostnet_event_aggregate() {
+        let user = Address::repeat_byte(0xab);
+        let jacked_in = GhostnetEvent::JackedIn(JackedInEvent {
+            meta: sample_metadata(),
+            user,
+            amount: U256::ZERO,
+            level: Level::Vault,
+            new_total: U256::ZERO,
+        });
+        let culled = GhostnetEvent::PositionCulled(PositionCulledEvent {
+            meta: sample_metadata(),
+            victim: user,
+            penalty_amount: U256::ZERO,
+            returned_amount: U256::ZERO,
+            new_entrant: Address::ZERO,
+        });
+        let transfer = GhostnetEvent::Transfer(TransferEvent {
+            meta: sample_metadata(),
+            from: user,
+            to: Address::ZERO,
+            value: U256::ZERO,
+        });
+
+        assert_eq!(
+            jacked_in.aggregate(),
+            format!("position:0x{}", "ab".repeat(20))
+        );
+        assert_eq!(culled.aggregate(), jacked_in.aggregate());
+        assert_eq!(transfer.aggregate(), "DataToken");
+    }
 }
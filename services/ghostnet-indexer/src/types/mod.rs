@@ -2,7 +2,7 @@
 //!
 //! This module contains all the core types used throughout the indexer:
 //!
-//! - [`enums`] - Game enumerations (`Level`, `BoostType`, `RoundType`, `ExitReason`)
+//! - [`enums`] - Game enumerations (`Level`, `BoostType`, `RoundType`, `ExitReason`, `LeaderboardType`)
 //! - [`primitives`] - Validated newtypes (`EthAddress`, `TokenAmount`, `GhostStreak`, `BlockNumber`)
 //! - [`events`] - Strongly-typed event structures from smart contracts
 //! - [`entities`] - Domain entities for database persistence
@@ -14,9 +14,11 @@ pub mod primitives;
 
 // Re-export commonly used types at module level
 pub use entities::{
-    Bet, Boost, Death, GlobalStats, LeaderboardEntry, LevelStats, LevelStatsDelta, Position,
-    PositionAction, PositionHistoryEntry, Round, Scan, ScanFinalizationData,
+    Bet, Boost, CascadeEarnings, CascadeShare, Death, ExitStreakCount, GlobalStats,
+    GlobalStatsDelta, HistoryCursor, LeaderboardEntry, LevelStats, LevelStatsDelta,
+    LevelSurvival, Position, PositionAction, PositionHistoryEntry, Round, Scan,
+    ScanCascadeEarnings, ScanFinalizationData,
 };
-pub use enums::{BoostType, ExitReason, Level, RoundType};
-pub use events::{EventMetadata, GhostnetEvent};
+pub use enums::{BoostType, ExitReason, LeaderboardType, Level, RoundType};
+pub use events::{DerivedEvent, EventMetadata, GhostnetEvent};
 pub use primitives::{BlockNumber, EthAddress, GhostStreak, TokenAmount};
@@ -7,10 +7,12 @@
 
 use std::fmt;
 use std::str::FromStr;
+use std::sync::LazyLock;
 
 use alloy::primitives::{Address, U256};
-use bigdecimal::BigDecimal;
-use serde::{Deserialize, Serialize};
+use bigdecimal::{BigDecimal, RoundingMode};
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use thiserror::Error;
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -152,13 +154,30 @@ pub enum InvalidAddress {
 // TOKEN AMOUNT
 // ═══════════════════════════════════════════════════════════════════════════════
 
+/// Decimals of the amounts stored and sent on-chain as wei.
+const WEI_DECIMALS: u8 = 18;
+
+/// One hundred percent, in basis points.
+const BPS_DENOMINATOR: u32 = 10_000;
+
+/// Largest amount representable as `U256` wei.
+static MAX_AMOUNT: LazyLock<TokenAmount> =
+    LazyLock::new(|| TokenAmount::from_wei(U256::MAX, WEI_DECIMALS));
+
 /// Non-negative token amount with arbitrary precision.
 ///
 /// Backed by `BigDecimal` for exact arithmetic. Amounts are always non-negative.
 /// Use this type for database persistence and domain logic. For on-chain
 /// interaction, convert to/from `U256`.
-#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(try_from = "String", into = "String")]
+///
+/// Arithmetic is bounded by [`TokenAmount::max_value`], the largest amount
+/// that still fits in `U256` wei (18 decimals): `checked_*` operations return
+/// `None` outside `0..=max`, `saturating_*` ones clamp to it.
+///
+/// Serializes as a plain decimal string of whole tokens (`"1.5"`). Both that
+/// and an integer number of wei (`1500000000000000000`) are accepted when
+/// deserializing.
+#[derive(Clone, PartialEq, Eq)]
 pub struct TokenAmount(BigDecimal);
 
 impl TokenAmount {
@@ -228,10 +247,29 @@ impl TokenAmount {
         self.0.sign() == bigdecimal::num_bigint::Sign::NoSign
     }
 
-    /// Saturating addition.
+    /// Largest amount representable as `U256` wei.
+    #[must_use]
+    pub fn max_value() -> Self {
+        MAX_AMOUNT.clone()
+    }
+
+    /// Checked addition, `None` if the sum exceeds [`TokenAmount::max_value`].
+    #[must_use]
+    pub fn checked_add(&self, other: &Self) -> Option<Self> {
+        let sum = Self(&self.0 + &other.0);
+        (sum <= *MAX_AMOUNT).then_some(sum)
+    }
+
+    /// Checked subtraction, `None` if `other` is larger than `self`.
+    #[must_use]
+    pub fn checked_sub(&self, other: &Self) -> Option<Self> {
+        Self::new(&self.0 - &other.0).ok()
+    }
+
+    /// Saturating addition (caps at [`TokenAmount::max_value`]).
     #[must_use]
     pub fn saturating_add(&self, other: &Self) -> Self {
-        Self(&self.0 + &other.0)
+        self.checked_add(other).unwrap_or_else(Self::max_value)
     }
 
     /// Saturating subtraction (floors at zero).
@@ -245,6 +283,42 @@ impl TokenAmount {
         }
     }
 
+    /// `bps` basis points of the amount, e.g. `percent_of(250)` is 2.5%.
+    ///
+    /// The result is rounded half up to whole wei, so a share of an
+    /// on-chain amount is itself representable on-chain.
+    #[must_use]
+    pub fn percent_of(&self, bps: u32) -> Self {
+        let share = &self.0 * BigDecimal::from(bps) / BigDecimal::from(BPS_DENOMINATOR);
+        Self(share.with_scale_round(i64::from(WEI_DECIMALS), RoundingMode::HalfUp))
+            .normalized()
+    }
+
+    /// Format with at most `precision` decimals, e.g. `"1234.5"` for two.
+    ///
+    /// Excess decimals are truncated rather than rounded, so a displayed
+    /// balance never exceeds the actual one. Trailing zeros are trimmed.
+    #[must_use]
+    pub fn format_with_precision(&self, precision: u32) -> String {
+        let truncated = self
+            .0
+            .with_scale_round(i64::from(precision), RoundingMode::Down)
+            .to_plain_string();
+        if truncated.contains('.') {
+            truncated
+                .trim_end_matches('0')
+                .trim_end_matches('.')
+                .to_string()
+        } else {
+            truncated
+        }
+    }
+
+    /// Drop trailing zeros, so equal amounts also print the same.
+    fn normalized(self) -> Self {
+        Self(self.0.normalized())
+    }
+
     /// Convert to `sqlx::types::BigDecimal` for database storage.
     ///
     /// Stores value as wei (18 decimal places) since DB uses NUMERIC(78, 0).
@@ -285,13 +359,14 @@ impl fmt::Debug for TokenAmount {
 
 impl fmt::Display for TokenAmount {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.0)
+        // Plain notation; `BigDecimal` shows tiny amounts like `1E-18`
+        f.write_str(&self.0.to_plain_string())
     }
 }
 
 impl From<TokenAmount> for String {
     fn from(amount: TokenAmount) -> Self {
-        amount.0.to_string()
+        amount.to_string()
     }
 }
 
@@ -309,6 +384,50 @@ impl Default for TokenAmount {
     }
 }
 
+impl Serialize for TokenAmount {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for TokenAmount {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(TokenAmountVisitor)
+    }
+}
+
+/// Accepts a decimal string of tokens or an integer number of wei.
+///
+/// JSON parsers read integers beyond `u64` as floats, which are rejected;
+/// larger amounts have to be sent as strings.
+struct TokenAmountVisitor;
+
+impl Visitor<'_> for TokenAmountVisitor {
+    type Value = TokenAmount;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a decimal token amount string or an integer amount of wei")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+        TokenAmount::parse(value).map_err(E::custom)
+    }
+
+    fn visit_u64<E: de::Error>(self, wei: u64) -> Result<Self::Value, E> {
+        Ok(TokenAmount::from_wei(U256::from(wei), WEI_DECIMALS))
+    }
+
+    fn visit_u128<E: de::Error>(self, wei: u128) -> Result<Self::Value, E> {
+        Ok(TokenAmount::from_wei(U256::from(wei), WEI_DECIMALS))
+    }
+
+    fn visit_i64<E: de::Error>(self, wei: i64) -> Result<Self::Value, E> {
+        u64::try_from(wei)
+            .map_err(|_| E::custom(InvalidAmount::Negative))
+            .and_then(|wei| self.visit_u64(wei))
+    }
+}
+
 impl PartialOrd for TokenAmount {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         Some(self.cmp(other))
@@ -611,6 +730,104 @@ mod tests {
             let result = a.saturating_sub(&b);
             assert!(result.is_zero());
         }
+
+        #[test]
+        fn checked_arithmetic_stays_within_u256_wei() {
+            let a = TokenAmount::parse("100").unwrap();
+            let b = TokenAmount::parse("30.5").unwrap();
+            assert_eq!(a.checked_add(&b).unwrap().to_string(), "130.5");
+            assert_eq!(a.checked_sub(&b).unwrap().to_string(), "69.5");
+            assert_eq!(b.checked_sub(&a), None);
+            assert!(a.checked_sub(&a).unwrap().is_zero());
+
+            let max = TokenAmount::max_value();
+            assert_eq!(max.to_wei(18), U256::MAX);
+            let one_wei = TokenAmount::from_wei(U256::from(1), 18);
+            assert_eq!(max.checked_add(&one_wei), None);
+            assert_eq!(max.saturating_add(&one_wei), max);
+        }
+
+        #[test]
+        fn percent_of_rounds_half_up_to_wei() {
+            let pool = TokenAmount::parse("1000").unwrap();
+            assert_eq!(pool.percent_of(250).to_string(), "25");
+            assert_eq!(pool.percent_of(10_000), pool);
+            assert!(pool.percent_of(0).is_zero());
+
+            let one_wei = TokenAmount::from_wei(U256::from(1), 18);
+            assert_eq!(one_wei.percent_of(5_000), one_wei);
+            assert!(one_wei.percent_of(4_999).is_zero());
+        }
+
+        #[test]
+        fn format_with_precision_truncates_and_trims() {
+            let amount = TokenAmount::parse("1234.5678").unwrap();
+            assert_eq!(amount.format_with_precision(2), "1234.56");
+            assert_eq!(amount.format_with_precision(0), "1234");
+            assert_eq!(amount.format_with_precision(10), "1234.5678");
+            assert_eq!(TokenAmount::parse("2.000").unwrap().format_with_precision(2), "2");
+            assert_eq!(TokenAmount::parse("1000").unwrap().format_with_precision(4), "1000");
+
+            let dust = TokenAmount::from_wei(U256::from(1), 18);
+            assert_eq!(dust.format_with_precision(4), "0");
+            assert_eq!(dust.to_string(), "0.000000000000000001");
+        }
+
+        #[test]
+        fn amounts_are_ordered() {
+            let small = TokenAmount::parse("1.5").unwrap();
+            let large = TokenAmount::parse("10").unwrap();
+            assert!(small < large);
+            assert_eq!(small.clone().max(large.clone()), large);
+            assert_eq!(small.clone().min(large), small);
+        }
+
+        #[test]
+        fn serde_accepts_strings_and_wei() {
+            let amount = TokenAmount::parse("1.5").unwrap();
+            assert_eq!(serde_json::to_string(&amount).unwrap(), "\"1.5\"");
+
+            let from_str: TokenAmount = serde_json::from_str("\"1.5\"").unwrap();
+            let from_wei: TokenAmount = serde_json::from_str("1500000000000000000").unwrap();
+            assert_eq!(from_str, amount);
+            assert_eq!(from_wei, amount);
+
+            assert!(serde_json::from_str::<TokenAmount>("\"-1\"").is_err());
+            assert!(serde_json::from_str::<TokenAmount>("-1").is_err());
+            assert!(serde_json::from_str::<TokenAmount>("1.5").is_err());
+        }
+
+        proptest::proptest! {
+            #[test]
+            fn percent_of_matches_integer_reference(wei: u128, bps in 0_u32..=20_000) {
+                // Half-up rounding of wei * bps / 10_000 in integers
+                let product = U256::from(wei) * U256::from(bps);
+                let (quotient, remainder) = product.div_rem(U256::from(10_000));
+                let expected = if remainder * U256::from(2) >= U256::from(10_000) {
+                    quotient + U256::from(1)
+                } else {
+                    quotient
+                };
+
+                let amount = TokenAmount::from_wei(U256::from(wei), 18);
+                proptest::prop_assert_eq!(amount.percent_of(bps).to_wei(18), expected);
+            }
+
+            #[test]
+            #[allow(clippy::redundant_clone)] // The first assertion moves its operands
+            fn serde_round_trips_both_forms(wei: u64) {
+                let amount = TokenAmount::from_wei(U256::from(wei), 18);
+                let json = serde_json::to_string(&amount).unwrap();
+                proptest::prop_assert_eq!(
+                    serde_json::from_str::<TokenAmount>(&json).unwrap(),
+                    amount.clone()
+                );
+                proptest::prop_assert_eq!(
+                    serde_json::from_str::<TokenAmount>(&wei.to_string()).unwrap(),
+                    amount
+                );
+            }
+        }
     }
 
     mod ghost_streak_tests {
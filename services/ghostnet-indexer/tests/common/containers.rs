@@ -45,11 +45,11 @@ impl TimescaleDb {
 }
 
 impl Image for TimescaleDb {
-    fn name(&self) -> &str {
+    fn name(&self) -> &'static str {
         "timescale/timescaledb"
     }
 
-    fn tag(&self) -> &str {
+    fn tag(&self) -> &'static str {
         "latest-pg16"
     }
 
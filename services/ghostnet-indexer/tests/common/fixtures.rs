@@ -2,7 +2,10 @@
 //!
 //! Uses rstest for pytest-style fixtures.
 
+use std::str::FromStr;
+
 use sqlx::PgPool;
+use sqlx::postgres::PgConnectOptions;
 use testcontainers::ContainerAsync;
 use testcontainers::runners::AsyncRunner;
 
@@ -18,6 +21,8 @@ pub struct TestDb {
     pub pool: PgPool,
     /// The PostgresStore wrapping the pool.
     pub store: PostgresStore,
+    /// Connection string of the test database.
+    pub url: String,
     /// The container (kept alive for the duration of the test).
     _container: ContainerAsync<TimescaleDb>,
 }
@@ -66,9 +71,29 @@ impl TestDb {
         Self {
             pool,
             store,
+            url: connection_string,
             _container: container,
         }
     }
+
+    /// Create a store for `chain_id`, migrated into `schema` of the same
+    /// database.
+    ///
+    /// # Panics
+    ///
+    /// Panics if connection or migrations fail.
+    pub async fn chain_store(&self, schema: &str, chain_id: u64) -> PostgresStore {
+        let options = PgConnectOptions::from_str(&self.url).expect("Invalid connection string");
+        let pool = PgPool::connect_with(PostgresStore::chain_options(options, schema, chain_id))
+            .await
+            .expect("Failed to connect to database");
+        let store = PostgresStore::new(pool);
+        store
+            .run_schema_migrations(schema)
+            .await
+            .expect("Failed to run migrations");
+        store
+    }
 }
 
 /// Connect to the database with retries.
@@ -118,6 +143,7 @@ pub mod position_fixtures {
             user_address: EthAddress::from_hex(user).expect("valid address"),
             level,
             amount: TokenAmount::from_wei(U256::from(1_000_000_000_000_000_000u128), 18), // 1 token
+            effective_stake: TokenAmount::from_wei(U256::from(1_000_000_000_000_000_000u128), 18),
             reward_debt: TokenAmount::zero(),
             entry_timestamp: now,
             last_add_timestamp: None,
@@ -126,6 +152,8 @@ pub mod position_fixtures {
             is_extracted: false,
             exit_reason: None,
             exit_timestamp: None,
+            survival_seconds: None,
+            scans_survived: None,
             extracted_amount: None,
             extracted_rewards: None,
             created_at_block: BlockNumber::new(1000),
@@ -187,6 +215,8 @@ pub mod scan_fixtures {
             distributed_upstream: None,
             protocol_fee: None,
             survivor_count: None,
+            finalized_block: None,
+            finalization_latency_ms: None,
         }
     }
 
@@ -257,3 +287,59 @@ pub mod death_fixtures {
         death
     }
 }
+
+/// Create test fixtures for `DeadPool` rounds and bets.
+pub mod market_fixtures {
+    use chrono::{Duration, Utc};
+    use uuid::Uuid;
+
+    use ghostnet_indexer::types::entities::{Bet, Pools, Round};
+    use ghostnet_indexer::types::enums::RoundType;
+    use ghostnet_indexer::types::primitives::{BlockNumber, EthAddress, TokenAmount};
+
+    /// Create an open death-count round.
+    pub fn create_test_round(round_id: &str) -> Round {
+        Round {
+            id: Uuid::new_v4(),
+            round_id: round_id.to_string(),
+            round_type: RoundType::DeathCount,
+            target_level: None,
+            line: TokenAmount::parse("10").expect("valid line"),
+            deadline: Utc::now() + Duration::hours(1),
+            over_pool: TokenAmount::zero(),
+            under_pool: TokenAmount::zero(),
+            is_resolved: false,
+            outcome: None,
+            resolve_time: None,
+            total_burned: None,
+            payout_multiple_bps: None,
+            house_take: None,
+        }
+    }
+
+    /// Create an unclaimed bet of `amount` on `round`.
+    pub fn create_test_bet(
+        round: &Round,
+        user: &str,
+        is_over: bool,
+        amount: TokenAmount,
+        log_index: u64,
+    ) -> Bet {
+        Bet {
+            id: Uuid::new_v4(),
+            round_id: round.id,
+            user_address: EthAddress::from_hex(user).expect("valid address"),
+            amount,
+            is_over,
+            block_number: BlockNumber::new(1000),
+            log_index,
+            placed_at: Utc::now(),
+            pools_before: Pools::default(),
+            implied_probability_bps: None,
+            payout_multiple_bps: None,
+            is_claimed: false,
+            winnings: None,
+            claimed_at: None,
+        }
+    }
+}
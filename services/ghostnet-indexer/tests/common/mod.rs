@@ -3,7 +3,9 @@
 //! This module provides shared test infrastructure:
 //! - Container setup for TimescaleDB
 //! - Test fixtures and builders
+//! - A proxy that follows a restarted container
 //! - Helper functions
 
 pub mod containers;
 pub mod fixtures;
+pub mod proxy;
@@ -0,0 +1,72 @@
+//! TCP proxy in front of a test database.
+//!
+//! Restarting a container gives it a new host port, which a connection pool
+//! cannot follow. The pool connects through the proxy instead, which tests
+//! point at the new port once the container is back. Until then every
+//! connection through the proxy fails, like a database that went away.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU16, Ordering};
+
+use tokio::io::copy_bidirectional;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+
+/// A proxy forwarding local connections to the database's host port.
+pub struct DbProxy {
+    port: u16,
+    upstream: Arc<AtomicU16>,
+    task: JoinHandle<()>,
+}
+
+impl DbProxy {
+    /// Start a proxy to `upstream` on a free local port.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no local port can be bound.
+    pub async fn start(upstream: u16) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("Failed to bind proxy");
+        let port = listener.local_addr().expect("Proxy has no address").port();
+        let upstream = Arc::new(AtomicU16::new(upstream));
+
+        let task = tokio::spawn({
+            let upstream = Arc::clone(&upstream);
+            async move {
+                while let Ok((mut client, _)) = listener.accept().await {
+                    let target = upstream.load(Ordering::SeqCst);
+                    tokio::spawn(async move {
+                        // Dropping the client without a server refuses it
+                        if let Ok(mut server) = TcpStream::connect(("127.0.0.1", target)).await {
+                            let _ = copy_bidirectional(&mut client, &mut server).await;
+                        }
+                    });
+                }
+            }
+        });
+
+        Self {
+            port,
+            upstream,
+            task,
+        }
+    }
+
+    /// Local port to connect to.
+    pub const fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// Forward new connections to `upstream`.
+    pub fn set_upstream(&self, upstream: u16) {
+        self.upstream.store(upstream, Ordering::SeqCst);
+    }
+}
+
+impl Drop for DbProxy {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
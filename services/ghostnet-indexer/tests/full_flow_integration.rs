@@ -8,14 +8,21 @@
 //!
 //! This tests Phase 3.14 of the implementation plan.
 
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+#[allow(dead_code)] // Not every test binary uses every shared fixture
 mod common;
 
 use std::sync::Arc;
+use std::time::Duration;
 
 use alloy::primitives::{Address, B256, U256};
 use alloy::rpc::types::Log;
 use alloy::sol_types::SolEvent;
+use async_trait::async_trait;
 use chrono::Utc;
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
 
 use common::fixtures::TestDb;
 use ghostnet_indexer::abi::ghost_core;
@@ -23,8 +30,9 @@ use ghostnet_indexer::handlers::{
     DeathHandler, EmissionsHandler, FeeHandler, MarketHandler, PositionHandler, ScanHandler,
     TokenHandler,
 };
-use ghostnet_indexer::indexer::EventRouter;
-use ghostnet_indexer::ports::{MockCache, PositionStore};
+use ghostnet_indexer::error::Result;
+use ghostnet_indexer::indexer::{CheckpointManager, EventRouter, Ingest, LogRouter, Pipeline};
+use ghostnet_indexer::ports::{IndexerStateStore, MockCache, PositionStore};
 use ghostnet_indexer::types::enums::Level;
 use ghostnet_indexer::types::events::EventMetadata;
 
@@ -33,6 +41,7 @@ use ghostnet_indexer::types::events::EventMetadata;
 // ═══════════════════════════════════════════════════════════════════════════════
 
 /// Create a test event metadata for a given block.
+#[allow(clippy::cast_possible_truncation)] // Test blocks stay below 256
 fn create_metadata(block_number: u64) -> EventMetadata {
     EventMetadata {
         block_number,
@@ -42,6 +51,9 @@ fn create_metadata(block_number: u64) -> EventMetadata {
         log_index: 0,
         timestamp: Utc::now(),
         contract: Address::ZERO,
+        chain_id: 6343,
+        tx_function: None,
+        tx_from: None,
     }
 }
 
@@ -65,7 +77,7 @@ fn create_jacked_in_log(user: Address, amount: U256, level: u8, new_total: U256)
         },
         block_hash: Some(B256::from([0x01; 32])),
         block_number: Some(100),
-        block_timestamp: Some(1234567890),
+        block_timestamp: Some(1_234_567_890),
         transaction_hash: Some(B256::from([0xAB; 32])),
         transaction_index: Some(0),
         log_index: Some(0),
@@ -90,7 +102,7 @@ fn create_stake_added_log(user: Address, amount: U256, new_total: U256) -> Log {
         },
         block_hash: Some(B256::from([0x02; 32])),
         block_number: Some(101),
-        block_timestamp: Some(1234567900),
+        block_timestamp: Some(1_234_567_900),
         transaction_hash: Some(B256::from([0xCD; 32])),
         transaction_index: Some(0),
         log_index: Some(0),
@@ -115,7 +127,7 @@ fn create_extracted_log(user: Address, amount: U256, rewards: U256) -> Log {
         },
         block_hash: Some(B256::from([0x03; 32])),
         block_number: Some(102),
-        block_timestamp: Some(1234567910),
+        block_timestamp: Some(1_234_567_910),
         transaction_hash: Some(B256::from([0xEF; 32])),
         transaction_index: Some(0),
         log_index: Some(0),
@@ -124,6 +136,7 @@ fn create_extracted_log(user: Address, amount: U256, rewards: U256) -> Log {
 }
 
 /// Create a router with all handlers wired to the test database.
+#[allow(clippy::type_complexity)]
 fn create_router_with_db(
     db: &TestDb,
 ) -> EventRouter<
@@ -145,10 +158,10 @@ fn create_router_with_db(
     let position_handler = PositionHandler::new(store.clone(), cache.clone());
     let scan_handler = ScanHandler::new(store.clone(), cache.clone());
     let death_handler = DeathHandler::new(store.clone(), store.clone(), cache.clone());
-    let market_handler = MarketHandler::new(store.clone(), cache.clone());
+    let market_handler = MarketHandler::new(store, cache.clone());
     let token_handler = TokenHandler::new(cache.clone());
     let fee_handler = FeeHandler::new(cache.clone());
-    let emissions_handler = EmissionsHandler::new(cache.clone());
+    let emissions_handler = EmissionsHandler::new(cache);
 
     EventRouter::new(
         position_handler,
@@ -302,8 +315,7 @@ async fn test_full_flow_multiple_users_independent() {
         let expected = Level::try_from(*expected_level).unwrap();
         assert_eq!(
             position.level, expected,
-            "user {} should have correct level",
-            i
+            "user {i} should have correct level"
         );
         assert!(position.is_alive);
     }
@@ -351,7 +363,7 @@ async fn test_unknown_event_not_handled() {
         },
         block_hash: Some(B256::from([0x01; 32])),
         block_number: Some(100),
-        block_timestamp: Some(1234567890),
+        block_timestamp: Some(1_234_567_890),
         transaction_hash: Some(B256::from([0xAB; 32])),
         transaction_index: Some(0),
         log_index: Some(0),
@@ -377,7 +389,7 @@ async fn test_empty_log_not_handled() {
         },
         block_hash: Some(B256::from([0x01; 32])),
         block_number: Some(100),
-        block_timestamp: Some(1234567890),
+        block_timestamp: Some(1_234_567_890),
         transaction_hash: Some(B256::from([0xAB; 32])),
         transaction_index: Some(0),
         log_index: Some(0),
@@ -463,3 +475,100 @@ async fn test_full_position_lifecycle() {
     assert_eq!(pos.level, Level::Darknet);
     assert_eq!(pos.amount.to_wei(18), new_amount);
 }
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// SHUTDOWN TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Router that never finishes logs from one block, simulating a hung handler.
+struct StallingRouter<R> {
+    inner: R,
+    stall_block: u64,
+}
+
+#[async_trait]
+impl<R: LogRouter> LogRouter for StallingRouter<R> {
+    async fn route_log(&self, log: &Log, meta: EventMetadata) -> Result<bool> {
+        if meta.block_number == self.stall_block {
+            std::future::pending::<()>().await;
+        }
+        self.inner.route_log(log, meta).await
+    }
+}
+
+/// Send one JackedIn per block for `users`, starting at block 100.
+async fn send_jack_ins(tx: &mpsc::Sender<Ingest>, users: &[Address]) {
+    let amount = U256::from(1_000_000_000_000_000_000u128);
+    for (block, user) in (100u64..).zip(users) {
+        let log = create_jacked_in_log(*user, amount, 2, amount);
+        tx.send(Ingest::Log(log, create_metadata(block)))
+            .await
+            .unwrap();
+    }
+}
+
+#[tokio::test]
+async fn test_shutdown_drains_queued_logs_and_checkpoints() {
+    let db = TestDb::new().await;
+    let router = create_router_with_db(&db);
+    let pipeline = Pipeline::new(router, CheckpointManager::new(db.store.clone()))
+        .with_grace_period(Duration::from_secs(10));
+
+    let users = [Address::from([0x81; 20]), Address::from([0x82; 20])];
+    let (tx, rx) = mpsc::channel(16);
+    send_jack_ins(&tx, &users).await;
+    tx.send(Ingest::BlocksComplete {
+        through: 101,
+        hash: B256::from([101; 32]),
+    })
+    .await
+    .unwrap();
+
+    // Shut down before the pipeline has seen anything; the queue must still drain
+    let shutdown = CancellationToken::new();
+    shutdown.cancel();
+    drop(tx);
+    let checkpoint = pipeline.run(rx, shutdown).await.unwrap();
+
+    assert_eq!(checkpoint.map(|b| b.value()), Some(101));
+    assert_eq!(db.store.get_last_block().await.unwrap().value(), 101);
+    for user in users {
+        let eth_addr = ghostnet_indexer::types::primitives::EthAddress::new(user.0.0);
+        let position = db.store.get_active_position(&eth_addr).await.unwrap();
+        assert!(position.is_some(), "queued log should be processed on shutdown");
+    }
+}
+
+#[tokio::test]
+async fn test_shutdown_grace_period_checkpoints_last_complete_block() {
+    let db = TestDb::new().await;
+    let router = StallingRouter {
+        inner: create_router_with_db(&db),
+        stall_block: 102,
+    };
+    let pipeline = Pipeline::new(router, CheckpointManager::new(db.store.clone()))
+        .with_grace_period(Duration::from_millis(200));
+
+    let users = [
+        Address::from([0x91; 20]),
+        Address::from([0x92; 20]),
+        Address::from([0x93; 20]),
+    ];
+    let (tx, rx) = mpsc::channel(16);
+    send_jack_ins(&tx, &users).await;
+
+    let shutdown = CancellationToken::new();
+    shutdown.cancel();
+    let checkpoint = pipeline.run(rx, shutdown).await.unwrap();
+    drop(tx);
+
+    // Block 102 never finished, so the checkpoint stops at 101 and it is replayed
+    assert_eq!(checkpoint.map(|b| b.value()), Some(101));
+    assert_eq!(db.store.get_last_block().await.unwrap().value(), 101);
+
+    for (i, user) in users.iter().enumerate() {
+        let eth_addr = ghostnet_indexer::types::primitives::EthAddress::new(user.0.0);
+        let position = db.store.get_active_position(&eth_addr).await.unwrap();
+        assert_eq!(position.is_some(), i < 2, "only logs before block 102 are persisted");
+    }
+}
@@ -48,6 +48,9 @@
 //! - **HTTP Tests**: Basic connectivity (testnet, may be flaky)
 //! - **MegaETH-Specific**: `eth_getLogsWithCursor`, `miniBlocks`, `stateChanges`
 
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+#[allow(dead_code)] // Not every test binary uses every shared fixture
 mod common;
 
 use std::sync::Arc;
@@ -140,10 +143,10 @@ fn get_testnet_http_fallback_urls() -> Vec<String> {
     ];
     
     // Add Alchemy if available (most reliable for testnet)
-    if let Ok(key) = std::env::var("ALCHEMY_API_KEY") {
-        if !key.is_empty() {
-            urls.insert(0, format!("{}{}", ALCHEMY_TESTNET_HTTP_BASE, key));
-        }
+    if let Ok(key) = std::env::var("ALCHEMY_API_KEY")
+        && !key.is_empty()
+    {
+        urls.insert(0, format!("{ALCHEMY_TESTNET_HTTP_BASE}{key}"));
     }
     
     urls
@@ -156,7 +159,7 @@ fn get_testnet_http_fallback_urls() -> Vec<String> {
 fn get_alchemy_testnet_ws_url() -> Option<String> {
     match std::env::var("ALCHEMY_API_KEY") {
         Ok(key) if !key.is_empty() => {
-            Some(format!("{}{}", ALCHEMY_TESTNET_WS_BASE, key))
+            Some(format!("{ALCHEMY_TESTNET_WS_BASE}{key}"))
         }
         _ => None,
     }
@@ -172,6 +175,7 @@ fn get_alchemy_testnet_ws_url() -> Option<String> {
 /// 2. Official carrot.megaeth.com (flaky)
 /// 3. Official timothy.megaeth.com (flaky)
 /// 4. Thirdweb
+#[allow(clippy::cast_sign_loss)]
 async fn create_testnet_http_provider()
 -> Result<impl Provider + Clone, Box<dyn std::error::Error + Send + Sync>> {
     let fallback_urls = get_testnet_http_fallback_urls();
@@ -196,10 +200,9 @@ async fn create_testnet_http_provider()
                     if chain_id == MEGAETH_TESTNET_CHAIN_ID {
                         info!(chain_id, rpc_url, "Connected to MegaETH testnet");
                         return Ok(provider);
-                    } else {
-                        warn!(expected = MEGAETH_TESTNET_CHAIN_ID, actual = chain_id, "Chain ID mismatch");
-                        break; // Try next endpoint
                     }
+                    warn!(expected = MEGAETH_TESTNET_CHAIN_ID, actual = chain_id, "Chain ID mismatch");
+                    break; // Try next endpoint
                 }
                 Ok(Err(e)) => {
                     let err_str = e.to_string();
@@ -427,7 +430,17 @@ async fn test_megaeth_fetch_logs() {
 /// NOTE: This test may hit rate limits on public RPCs (429 errors).
 #[tokio::test]
 #[ignore = "requires network access and Docker; testnet is flaky - use mainnet E2E instead"]
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_possible_wrap,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss,
+    clippy::panic,
+    clippy::too_many_lines,
+)]
 async fn test_live_indexing_pipeline() {
+    const MAX_CONSECUTIVE_ERRORS: u32 = 5;
+
     tracing_subscriber::fmt::try_init().ok();
     info!("Starting TESTNET live indexing pipeline test...");
     info!("WARNING: Testnet is flaky. For reliable E2E, use test_mainnet_e2e_pipeline");
@@ -505,7 +518,6 @@ async fn test_live_indexing_pipeline() {
     );
 
     let mut consecutive_errors = 0u32;
-    const MAX_CONSECUTIVE_ERRORS: u32 = 5;
     
     while tokio::time::Instant::now() < deadline {
         // Get latest block with retry logic for transient errors
@@ -521,18 +533,16 @@ async fn test_live_indexing_pipeline() {
                 // Retry transient errors with backoff
                 if err_str.contains("429") || err_str.contains("502") || err_str.contains("503") 
                    || err_str.contains("504") || err_str.contains("timeout") || err_str.contains("1015") {
-                    if consecutive_errors >= MAX_CONSECUTIVE_ERRORS {
-                        panic!(
-                            "Too many consecutive RPC errors ({}/{}): {}",
-                            consecutive_errors, MAX_CONSECUTIVE_ERRORS, e
-                        );
-                    }
+                    assert!(
+                        consecutive_errors < MAX_CONSECUTIVE_ERRORS,
+                        "Too many consecutive RPC errors ({consecutive_errors}/{MAX_CONSECUTIVE_ERRORS}): {e}"
+                    );
                     warn!(error = %e, consecutive_errors, "Transient error, backing off");
-                    sleep(Duration::from_secs(consecutive_errors as u64)).await;
+                    sleep(Duration::from_secs(u64::from(consecutive_errors))).await;
                     continue;
                 }
                 
-                panic!("Failed to get latest block: {}", e);
+                panic!("Failed to get latest block: {e}");
             }
         };
 
@@ -801,6 +811,7 @@ struct LogsWithCursorFilter {
 /// See: docs/MegaETH_RealtimeAPI.md
 #[tokio::test]
 #[ignore = "requires network access; testnet endpoint may be flaky"]
+#[allow(clippy::panic, clippy::too_many_lines)]
 async fn test_eth_get_logs_with_cursor() {
     tracing_subscriber::fmt::try_init().ok();
     info!("Testing eth_getLogsWithCursor (MegaETH paginated log API)...");
@@ -829,9 +840,9 @@ async fn test_eth_get_logs_with_cursor() {
 
     // Check for RPC errors
     if let Some(error) = block_response.get("error") {
-        let code = error.get("code").and_then(|c| c.as_i64()).unwrap_or(0);
+        let code = error.get("code").and_then(serde_json::Value::as_i64).unwrap_or(0);
         let message = error.get("message").and_then(|m| m.as_str()).unwrap_or("Unknown");
-        panic!("RPC error getting block number ({}): {}", code, message);
+        panic!("RPC error getting block number ({code}): {message}");
     }
     
     let latest_block_hex = block_response["result"]
@@ -880,7 +891,7 @@ async fn test_eth_get_logs_with_cursor() {
     // Check for errors (the method might not be available on all endpoints)
     if let Some(error) = response.get("error") {
         let error_msg = error.get("message").and_then(|m| m.as_str()).unwrap_or("Unknown error");
-        let error_code = error.get("code").and_then(|c| c.as_i64()).unwrap_or(0);
+        let error_code = error.get("code").and_then(serde_json::Value::as_i64).unwrap_or(0);
         
         if error_code == -32601 {
             // Method not found - endpoint doesn't support this MegaETH-specific API
@@ -894,7 +905,7 @@ async fn test_eth_get_logs_with_cursor() {
             return;
         }
         
-        panic!("RPC error: {} (code: {})", error_msg, error_code);
+        panic!("RPC error: {error_msg} (code: {error_code})");
     }
 
     // Parse the successful response
@@ -986,9 +997,7 @@ async fn test_eth_get_logs_with_cursor() {
                     sleep(backoff).await;
                     continue;
                 }
-                if !status.is_success() {
-                    panic!("HTTP error {} on batch {}", status, batch);
-                }
+                assert!(status.is_success(), "HTTP error {status} on batch {batch}");
                 
                 let body_text = response.text().await.expect("Failed to read response body");
                 if body_text.is_empty() {
@@ -1008,16 +1017,16 @@ async fn test_eth_get_logs_with_cursor() {
             
             // Check for RPC errors
             if let Some(error) = response_json.get("error") {
-                let code = error.get("code").and_then(|c| c.as_i64()).unwrap_or(0);
+                let code = error.get("code").and_then(serde_json::Value::as_i64).unwrap_or(0);
                 let msg = error.get("message").and_then(|m| m.as_str()).unwrap_or("Unknown");
-                panic!("RPC error on batch {} ({}): {}", batch, code, msg);
+                panic!("RPC error on batch {batch} ({code}): {msg}");
             }
 
             let result = response_json.get("result")
-                .unwrap_or_else(|| panic!("Missing result field on batch {}", batch));
+                .unwrap_or_else(|| panic!("Missing result field on batch {batch}"));
             
             let parsed: LogsWithCursorResponse = serde_json::from_value(result.clone())
-                .unwrap_or_else(|e| panic!("Failed to parse LogsWithCursorResponse on batch {}: {}", batch, e));
+                .unwrap_or_else(|e| panic!("Failed to parse LogsWithCursorResponse on batch {batch}: {e}"));
 
             info!(
                 batch,
@@ -1029,13 +1038,11 @@ async fn test_eth_get_logs_with_cursor() {
             total_logs += parsed.logs.len();
             batches += 1;
 
-            match parsed.cursor {
-                Some(next) => current_cursor = next,
-                None => {
-                    info!("No more cursors, query complete");
-                    break;
-                }
-            }
+            let Some(next) = parsed.cursor else {
+                info!("No more cursors, query complete");
+                break;
+            };
+            current_cursor = next;
         }
     }
 
@@ -1093,6 +1100,7 @@ struct MiniBlocksTestResult {
 }
 
 /// Helper: Test miniBlocks subscription on a specific WebSocket endpoint
+#[allow(clippy::too_many_lines)]
 async fn test_mini_blocks_on_endpoint(ws_url: &str, endpoint_name: &str) -> MiniBlocksTestResult {
     use tokio_tungstenite::{connect_async, tungstenite::Message};
     use futures::SinkExt;
@@ -1108,7 +1116,7 @@ async fn test_mini_blocks_on_endpoint(ws_url: &str, endpoint_name: &str) -> Mini
                 connected: false,
                 subscription_confirmed: false,
                 mini_blocks_received: 0,
-                error: Some(format!("Connection failed: {}", e)),
+                error: Some(format!("Connection failed: {e}")),
             };
         }
     };
@@ -1132,7 +1140,7 @@ async fn test_mini_blocks_on_endpoint(ws_url: &str, endpoint_name: &str) -> Mini
             connected: true,
             subscription_confirmed: false,
             mini_blocks_received: 0,
-            error: Some(format!("Failed to send subscription: {}", e)),
+            error: Some(format!("Failed to send subscription: {e}")),
         };
     }
 
@@ -1159,14 +1167,12 @@ async fn test_mini_blocks_on_endpoint(ws_url: &str, endpoint_name: &str) -> Mini
                 }
             }
             Ok(Some(Ok(Message::Close(frame)))) => {
-                error_msg = Some(format!("WebSocket closed: {:?}", frame));
+                error_msg = Some(format!("WebSocket closed: {frame:?}"));
                 break;
             }
-            Ok(Some(Ok(Message::Ping(_)))) => continue,
-            Ok(Some(Ok(Message::Pong(_)))) => continue,
-            Ok(Some(Ok(Message::Frame(_)))) => continue,
+            Ok(Some(Ok(Message::Ping(_) | Message::Pong(_) | Message::Frame(_)))) => continue,
             Ok(Some(Err(e))) => {
-                error_msg = Some(format!("WebSocket error: {}", e));
+                error_msg = Some(format!("WebSocket error: {e}"));
                 break;
             }
             Ok(None) => {
@@ -1195,19 +1201,19 @@ async fn test_mini_blocks_on_endpoint(ws_url: &str, endpoint_name: &str) -> Mini
         };
 
         // Check for subscription confirmation
-        if let Some(result) = parsed.get("result") {
-            if subscription_id.is_none() {
-                subscription_id = result.as_str().map(String::from);
-                info!(endpoint_name, subscription_id = ?subscription_id, "Subscription confirmed");
-                continue;
-            }
+        if let Some(result) = parsed.get("result")
+            && subscription_id.is_none()
+        {
+            subscription_id = result.as_str().map(String::from);
+            info!(endpoint_name, subscription_id = ?subscription_id, "Subscription confirmed");
+            continue;
         }
 
         // Check for error
         if let Some(error) = parsed.get("error") {
             let err_msg = error.get("message").and_then(|m| m.as_str()).unwrap_or("Unknown");
-            let err_code = error.get("code").and_then(|c| c.as_i64()).unwrap_or(0);
-            error_msg = Some(format!("RPC error {}: {}", err_code, err_msg));
+            let err_code = error.get("code").and_then(serde_json::Value::as_i64).unwrap_or(0);
+            error_msg = Some(format!("RPC error {err_code}: {err_msg}"));
             break;
         }
 
@@ -1359,8 +1365,7 @@ async fn test_mini_blocks_subscription() {
         ╠══════════════════════════════════════════════════════════════════════════════╣\n\
         ║  WORKAROUND: Use HTTP polling with eth_getBlockByNumber for now.             ║\n\
         ╚══════════════════════════════════════════════════════════════════════════════╝\n\n\
-        Results: {:?}",
-        results
+        Results: {results:?}"
     );
 
     info!("✓ miniBlocks subscription test passed");
@@ -1378,6 +1383,7 @@ struct StateChangesTestResult {
 }
 
 /// Helper: Test stateChanges subscription on a specific WebSocket endpoint
+#[allow(clippy::too_many_lines)]
 async fn test_state_changes_on_endpoint(ws_url: &str, endpoint_name: &str) -> StateChangesTestResult {
     use tokio_tungstenite::{connect_async, tungstenite::Message};
     use futures::SinkExt;
@@ -1393,7 +1399,7 @@ async fn test_state_changes_on_endpoint(ws_url: &str, endpoint_name: &str) -> St
                 connected: false,
                 subscription_confirmed: false,
                 state_changes_received: 0,
-                error: Some(format!("Connection failed: {}", e)),
+                error: Some(format!("Connection failed: {e}")),
             };
         }
     };
@@ -1423,7 +1429,7 @@ async fn test_state_changes_on_endpoint(ws_url: &str, endpoint_name: &str) -> St
             connected: true,
             subscription_confirmed: false,
             state_changes_received: 0,
-            error: Some(format!("Failed to send subscription: {}", e)),
+            error: Some(format!("Failed to send subscription: {e}")),
         };
     }
 
@@ -1438,18 +1444,18 @@ async fn test_state_changes_on_endpoint(ws_url: &str, endpoint_name: &str) -> St
         let msg = match timeout(Duration::from_secs(5), ws_stream.next()).await {
             Ok(Some(Ok(Message::Text(text)))) => text,
             Ok(Some(Ok(Message::Close(frame)))) => {
-                error_msg = Some(format!("WebSocket closed: {:?}", frame));
+                error_msg = Some(format!("WebSocket closed: {frame:?}"));
                 break;
             }
             Ok(Some(Err(e))) => {
-                error_msg = Some(format!("WebSocket error: {}", e));
+                error_msg = Some(format!("WebSocket error: {e}"));
                 break;
             }
             Ok(None) => {
                 error_msg = Some("WebSocket stream ended".to_string());
                 break;
             }
-            Err(_) => continue, // Timeout waiting for message
+            // Timeout waiting for message, or a non-text frame
             _ => continue,
         };
 
@@ -1462,42 +1468,43 @@ async fn test_state_changes_on_endpoint(ws_url: &str, endpoint_name: &str) -> St
         };
 
         // Check for subscription confirmation
-        if let Some(result) = parsed.get("result") {
-            if subscription_id.is_none() && result.is_string() {
-                subscription_id = result.as_str().map(String::from);
-                info!(endpoint_name, subscription_id = ?subscription_id, "Subscription confirmed");
-                continue;
-            }
+        if let Some(result) = parsed.get("result")
+            && subscription_id.is_none()
+            && result.is_string()
+        {
+            subscription_id = result.as_str().map(String::from);
+            info!(endpoint_name, subscription_id = ?subscription_id, "Subscription confirmed");
+            continue;
         }
 
         // Check for error
         if let Some(error) = parsed.get("error") {
             let err_msg = error.get("message").and_then(|m| m.as_str()).unwrap_or("Unknown");
-            let err_code = error.get("code").and_then(|c| c.as_i64()).unwrap_or(0);
-            error_msg = Some(format!("RPC error {}: {}", err_code, err_msg));
+            let err_code = error.get("code").and_then(serde_json::Value::as_i64).unwrap_or(0);
+            error_msg = Some(format!("RPC error {err_code}: {err_msg}"));
             break;
         }
 
         // Check for state change notification
-        if let Some(params) = parsed.get("params") {
-            if let Some(result) = params.get("result") {
-                state_changes_received += 1;
-                
-                let address = result.get("address").and_then(|a| a.as_str()).unwrap_or("?");
-                let balance = result.get("balance").and_then(|b| b.as_str());
+        if let Some(params) = parsed.get("params")
+            && let Some(result) = params.get("result")
+        {
+            state_changes_received += 1;
+            
+            let address = result.get("address").and_then(|a| a.as_str()).unwrap_or("?");
+            let balance = result.get("balance").and_then(|b| b.as_str());
 
-                info!(
-                    endpoint_name,
-                    state_changes_received,
-                    address,
-                    balance = ?balance,
-                    "Received state change"
-                );
+            info!(
+                endpoint_name,
+                state_changes_received,
+                address,
+                balance = ?balance,
+                "Received state change"
+            );
 
-                if state_changes_received >= 3 {
-                    info!(endpoint_name, "Received 3 state changes, success!");
-                    break;
-                }
+            if state_changes_received >= 3 {
+                info!(endpoint_name, "Received 3 state changes, success!");
+                break;
             }
         }
     }
@@ -1615,8 +1622,7 @@ async fn test_state_changes_subscription() {
         ╠══════════════════════════════════════════════════════════════════════════════╣\n\
         ║  WORKAROUND: Use HTTP polling for account state changes.                     ║\n\
         ╚══════════════════════════════════════════════════════════════════════════════╝\n\n\
-        Results: {:?}",
-        results
+        Results: {results:?}"
     );
 
     info!("✓ stateChanges subscription test passed");
@@ -1651,7 +1657,16 @@ async fn test_state_changes_subscription() {
 /// ```
 #[tokio::test]
 #[ignore = "requires network access and Docker; primary E2E verification"]
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_possible_wrap,
+    clippy::panic,
+    clippy::too_many_lines,
+)]
 async fn test_mainnet_e2e_pipeline() {
+    use futures::SinkExt;
+    use tokio_tungstenite::{connect_async, tungstenite::Message};
+
     install_crypto_provider();
     tracing_subscriber::fmt::try_init().ok();
     
@@ -1696,9 +1711,6 @@ async fn test_mainnet_e2e_pipeline() {
     info!("");
     info!("STEP 2: Connecting to MegaETH Mainnet WebSocket...");
     info!("  URL: {}", MEGAETH_MAINNET_WS_RPC);
-    
-    use tokio_tungstenite::{connect_async, tungstenite::Message};
-    use futures::SinkExt;
 
     let (mut ws_stream, _response) = connect_async(MEGAETH_MAINNET_WS_RPC)
         .await
@@ -1742,7 +1754,7 @@ async fn test_mainnet_e2e_pipeline() {
         }
         
         if let Some(error) = parsed.get("error") {
-            panic!("Subscription failed: {:?}", error);
+            panic!("Subscription failed: {error:?}");
         }
     }
 
@@ -1775,47 +1787,47 @@ async fn test_mainnet_e2e_pipeline() {
         };
 
         // Check for mini block data
-        if let Some(params) = parsed.get("params") {
-            if let Some(result) = params.get("result") {
-                // Parse mini block
-                let block_number = result.get("block_number").and_then(|v| v.as_u64()).unwrap_or(0);
-                let mini_block_number = result.get("number").and_then(|v| v.as_u64()).unwrap_or(0);
-                let block_timestamp = result.get("block_timestamp").and_then(|v| v.as_u64()).unwrap_or(0);
-                let mini_block_timestamp = result.get("timestamp").and_then(|v| v.as_u64()).unwrap_or(0);
-                let index = result.get("index").and_then(|v| v.as_u64()).unwrap_or(0) as i32;
-                let gas_used = result.get("gas_used").and_then(|v| v.as_u64()).unwrap_or(0);
-                let tx_count = result.get("transactions").and_then(|v| v.as_array()).map(|a| a.len()).unwrap_or(0) as i32;
-                let receipt_count = result.get("receipts").and_then(|v| v.as_array()).map(|a| a.len()).unwrap_or(0) as i32;
-
-                blocks_received += 1;
-
-                // Store in database
-                let insert_result = sqlx::query(
-                    r#"
-                    INSERT INTO mini_blocks 
-                    (block_number, mini_block_number, block_timestamp, mini_block_timestamp, block_index, gas_used, tx_count, receipt_count)
-                    VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
-                    ON CONFLICT (mini_block_number) DO NOTHING
-                    "#,
-                )
-                .bind(block_number as i64)
-                .bind(mini_block_number as i64)
-                .bind(block_timestamp as i64)
-                .bind(mini_block_timestamp as i64)
-                .bind(index)
-                .bind(gas_used as i64)
-                .bind(tx_count)
-                .bind(receipt_count)
-                .execute(&db.pool)
-                .await;
+        if let Some(params) = parsed.get("params")
+            && let Some(result) = params.get("result")
+        {
+            // Parse mini block
+            let block_number = result.get("block_number").and_then(serde_json::Value::as_u64).unwrap_or(0);
+            let mini_block_number = result.get("number").and_then(serde_json::Value::as_u64).unwrap_or(0);
+            let block_timestamp = result.get("block_timestamp").and_then(serde_json::Value::as_u64).unwrap_or(0);
+            let mini_block_timestamp = result.get("timestamp").and_then(serde_json::Value::as_u64).unwrap_or(0);
+            let index = result.get("index").and_then(serde_json::Value::as_u64).unwrap_or(0) as i32;
+            let gas_used = result.get("gas_used").and_then(serde_json::Value::as_u64).unwrap_or(0);
+            let tx_count = result.get("transactions").and_then(|v| v.as_array()).map_or(0, std::vec::Vec::len) as i32;
+            let receipt_count = result.get("receipts").and_then(|v| v.as_array()).map_or(0, std::vec::Vec::len) as i32;
+
+            blocks_received += 1;
+
+            // Store in database
+            let insert_result = sqlx::query(
+                r#"
+                INSERT INTO mini_blocks 
+                (block_number, mini_block_number, block_timestamp, mini_block_timestamp, block_index, gas_used, tx_count, receipt_count)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                ON CONFLICT (mini_block_number) DO NOTHING
+                "#,
+            )
+            .bind(block_number as i64)
+            .bind(mini_block_number as i64)
+            .bind(block_timestamp as i64)
+            .bind(mini_block_timestamp as i64)
+            .bind(index)
+            .bind(gas_used as i64)
+            .bind(tx_count)
+            .bind(receipt_count)
+            .execute(&db.pool)
+            .await;
 
-                if insert_result.is_ok() {
-                    blocks_stored += 1;
-                }
+            if insert_result.is_ok() {
+                blocks_stored += 1;
+            }
 
-                if blocks_received % 5 == 0 {
-                    info!("  ... received {} / {} mini blocks", blocks_received, target_blocks);
-                }
+            if blocks_received.is_multiple_of(5) {
+                info!("  ... received {} / {} mini blocks", blocks_received, target_blocks);
             }
         }
     }
@@ -1823,8 +1835,8 @@ async fn test_mainnet_e2e_pipeline() {
     info!("  ✓ Received {} mini blocks", blocks_received);
     info!("  ✓ Stored {} mini blocks in database", blocks_stored);
 
-    assert!(blocks_received >= 5, "Expected at least 5 mini blocks, got {}", blocks_received);
-    assert!(blocks_stored >= 5, "Expected at least 5 stored blocks, got {}", blocks_stored);
+    assert!(blocks_received >= 5, "Expected at least 5 mini blocks, got {blocks_received}");
+    assert!(blocks_stored >= 5, "Expected at least 5 stored blocks, got {blocks_stored}");
 
     // ─────────────────────────────────────────────────────────────────────────────
     // STEP 5: Read back from database and verify
@@ -0,0 +1,245 @@
+//! Multi-chain integration tests: two chains indexed side by side.
+//!
+//! Each chain gets its own schema of one database, its own mocked provider
+//! and its own pipeline, as `run` sets them up for the `[[chains]]` of the
+//! config. The tests check that nothing indexed from one chain shows up in
+//! the positions, stats, cursors or archive of the other.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+#[allow(dead_code)] // Not every test binary uses every shared fixture
+mod common;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use alloy::primitives::{Address, B256, U256};
+use alloy::providers::ProviderBuilder;
+use alloy::rpc::types::{Block, Log, Transaction};
+use alloy::sol_types::SolEvent;
+use alloy::transports::mock::Asserter;
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+
+use common::fixtures::TestDb;
+use ghostnet_indexer::abi::ghost_core;
+use ghostnet_indexer::config::{ContractAddresses, StatsSettings};
+use ghostnet_indexer::handlers::{
+    DeathHandler, EmissionsHandler, FeeHandler, MarketHandler, PositionHandler, ScanHandler,
+    TokenHandler,
+};
+use ghostnet_indexer::indexer::{
+    BlockProcessor, CheckpointManager, Contract, ContractRegistry, ContractScope, EventRouter,
+    Pipeline, StatsAggregator,
+};
+use ghostnet_indexer::ports::{IndexerStateStore, PositionStore, RawLogStore};
+use ghostnet_indexer::store::{MemoryCache, PostgresStore};
+use ghostnet_indexer::types::enums::Level;
+use ghostnet_indexer::types::primitives::{BlockNumber, EthAddress};
+
+const TESTNET: u64 = 6343;
+const MAINNET: u64 = 4326;
+
+/// GhostCore's address on both chains.
+const GHOST_CORE: Address = Address::with_last_byte(1);
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TEST HELPERS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// A JackedIn of 1 DATA by `user` at `level`, in `block`.
+#[allow(clippy::cast_possible_truncation)]
+fn jacked_in(user: Address, level: u8, block: u64) -> Log {
+    let amount = U256::from(1_000_000_000_000_000_000u128);
+    let event = ghost_core::JackedIn {
+        user,
+        amount,
+        level,
+        newTotal: amount,
+    };
+    Log {
+        inner: alloy::primitives::Log {
+            address: GHOST_CORE,
+            data: event.encode_log_data(),
+        },
+        block_hash: Some(B256::with_last_byte(block as u8)),
+        block_number: Some(block),
+        transaction_hash: Some(B256::repeat_byte(block as u8)),
+        transaction_index: Some(0),
+        log_index: Some(0),
+        ..Log::default()
+    }
+}
+
+fn addresses() -> ContractAddresses {
+    ContractAddresses {
+        ghost_core: GHOST_CORE.to_string(),
+        trace_scan: "0x0000000000000000000000000000000000000002".into(),
+        dead_pool: "0x0000000000000000000000000000000000000003".into(),
+        data_token: "0x0000000000000000000000000000000000000004".into(),
+        fee_router: "0x0000000000000000000000000000000000000005".into(),
+        rewards_distributor: "0x0000000000000000000000000000000000000006".into(),
+        disabled: vec![],
+        code_hashes: HashMap::new(),
+        additional: HashMap::new(),
+    }
+}
+
+/// Backfill GhostCore over `from..=to` of `chain_id` into `store`, from a
+/// provider that serves only `log`.
+async fn index_chain(store: &PostgresStore, chain_id: u64, log: Log, (from, to): (u64, u64)) {
+    let asserter = Asserter::new();
+    asserter.push_success(&vec![log]);
+    asserter.push_success(&Block::<Transaction>::default());
+    asserter.push_success(&Block::<Transaction>::default());
+    let provider = ProviderBuilder::new().connect_mocked_client(asserter.clone());
+
+    let registry = ContractRegistry::from_config(&addresses()).unwrap();
+    let (tx, rx) = mpsc::channel(16);
+    let processor = BlockProcessor::new(Arc::new(provider), registry, tx, None)
+        .with_chain_id(chain_id)
+        .with_scope(ContractScope::Only(Contract::GhostCore));
+
+    let shared = Arc::new(store.clone());
+    let cache = Arc::new(MemoryCache::new());
+    let router = EventRouter::new(
+        PositionHandler::new(shared.clone(), cache.clone()),
+        ScanHandler::new(shared.clone(), cache.clone()),
+        DeathHandler::new(shared.clone(), shared.clone(), cache.clone()),
+        MarketHandler::new(shared.clone(), cache.clone()),
+        TokenHandler::new(cache.clone()),
+        FeeHandler::new(cache.clone()),
+        EmissionsHandler::new(cache),
+    );
+    let checkpoints = CheckpointManager::for_contracts(store.clone(), [Contract::GhostCore])
+        .with_chain_id(chain_id);
+    let pipeline = Pipeline::new(router, checkpoints).with_archive(Some(shared));
+
+    let backfill = tokio::spawn(async move { processor.backfill(from, to).await });
+    pipeline.run(rx, CancellationToken::new()).await.unwrap();
+    backfill.await.unwrap().unwrap();
+    assert!(asserter.read_q().is_empty());
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// ISOLATION TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn chains_are_indexed_in_isolation() {
+    let db = TestDb::new().await;
+    let testnet = db.chain_store("chain_6343", TESTNET).await;
+    let mainnet = db.chain_store("chain_4326", MAINNET).await;
+
+    let alice = Address::repeat_byte(0x11);
+    let bob = Address::repeat_byte(0x22);
+    tokio::join!(
+        index_chain(&testnet, TESTNET, jacked_in(alice, 2, 100), (90, 110)),
+        index_chain(&mainnet, MAINNET, jacked_in(bob, 4, 500), (490, 510)),
+    );
+
+    // Positions
+    let (alice, bob) = (EthAddress::from(alice), EthAddress::from(bob));
+    let position = testnet.get_active_position(&alice).await.unwrap().unwrap();
+    assert_eq!(position.level, Level::Mainframe);
+    assert!(testnet.get_active_position(&bob).await.unwrap().is_none());
+    let position = mainnet.get_active_position(&bob).await.unwrap().unwrap();
+    assert_eq!(position.level, Level::Darknet);
+    assert!(mainnet.get_active_position(&alice).await.unwrap().is_none());
+    assert!(
+        db.store
+            .get_active_position(&alice)
+            .await
+            .unwrap()
+            .is_none()
+    );
+
+    // Stats
+    for (store, alive, dead) in [
+        (&testnet, Level::Mainframe, Level::Darknet),
+        (&mainnet, Level::Darknet, Level::Mainframe),
+    ] {
+        let stats = StatsAggregator::new(
+            Arc::new(store.clone()),
+            Arc::new(MemoryCache::new()),
+            &StatsSettings::default(),
+        )
+        .recompute_from_db()
+        .await
+        .unwrap();
+        let alive_count = |level| {
+            stats
+                .iter()
+                .find(|s| s.level == level)
+                .map_or(0, |s| s.alive_count)
+        };
+        assert_eq!(alive_count(alive), 1);
+        assert_eq!(alive_count(dead), 0);
+    }
+
+    // Cursors
+    let cursor = |block| Some(BlockNumber::new(block));
+    assert_eq!(
+        testnet.get_cursor(Contract::GhostCore).await.unwrap(),
+        cursor(110)
+    );
+    assert_eq!(
+        mainnet.get_cursor(Contract::GhostCore).await.unwrap(),
+        cursor(510)
+    );
+    assert_eq!(
+        db.store.get_cursor(Contract::GhostCore).await.unwrap(),
+        None
+    );
+
+    // Archive
+    let archived = testnet
+        .get_raw_logs(BlockNumber::new(0), BlockNumber::new(1_000), None, 10)
+        .await
+        .unwrap();
+    assert_eq!(archived.len(), 1);
+    assert_eq!(
+        (archived[0].block_number.value(), archived[0].chain_id),
+        (100, TESTNET)
+    );
+    let archived = mainnet
+        .get_raw_logs(BlockNumber::new(0), BlockNumber::new(1_000), None, 10)
+        .await
+        .unwrap();
+    assert_eq!(archived.len(), 1);
+    assert_eq!(
+        (archived[0].block_number.value(), archived[0].chain_id),
+        (500, MAINNET)
+    );
+}
+
+#[tokio::test]
+#[allow(clippy::cast_possible_wrap)]
+async fn rows_default_to_the_chain_of_their_schema() {
+    let db = TestDb::new().await;
+    let testnet = db.chain_store("chain_6343", TESTNET).await;
+    let mainnet = db.chain_store("chain_4326", MAINNET).await;
+
+    let alice = Address::repeat_byte(0x11);
+    let bob = Address::repeat_byte(0x22);
+    index_chain(&testnet, TESTNET, jacked_in(alice, 2, 100), (90, 110)).await;
+    index_chain(&mainnet, MAINNET, jacked_in(bob, 4, 500), (490, 510)).await;
+
+    for (store, chain_id) in [(&testnet, TESTNET), (&mainnet, MAINNET)] {
+        let chains: Vec<i64> = sqlx::query_scalar("SELECT DISTINCT chain_id FROM positions")
+            .fetch_all(store.pool())
+            .await
+            .unwrap();
+        assert_eq!(chains, vec![chain_id as i64]);
+    }
+
+    // Migrating the chain schemas left the legacy chain's tables in `public`
+    let tables: i64 = sqlx::query_scalar(
+        "SELECT count(*) FROM information_schema.tables \
+         WHERE table_schema = 'public' AND table_name IN ('positions', 'deaths', 'scans')",
+    )
+    .fetch_one(&db.pool)
+    .await
+    .unwrap();
+    assert_eq!(tables, 3);
+}
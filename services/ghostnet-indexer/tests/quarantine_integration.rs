@@ -0,0 +1,217 @@
+//! Integration tests for quarantining logs whose handler fails.
+//!
+//! These tests route canned `GhostCore` logs through the position handler
+//! against a real TimescaleDB instance in Docker, with a stake added to a
+//! position that was never opened as the poison log, and check that it is
+//! quarantined, blocks the user's later logs and is released once retried.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+#[allow(dead_code)] // Not every test binary uses every shared fixture
+mod common;
+
+use std::sync::Arc;
+
+use alloy::primitives::{Address, B256, U256};
+use alloy::rpc::types::Log;
+use alloy::sol_types::SolEvent;
+use chrono::DateTime;
+
+use common::fixtures::TestDb;
+use ghostnet_indexer::abi::ghost_core;
+use ghostnet_indexer::config::QuarantineSettings;
+use ghostnet_indexer::error::AppError;
+use ghostnet_indexer::handlers::{
+    DeathHandler, EmissionsHandler, FeeHandler, MarketHandler, PositionHandler, ScanHandler,
+    TokenHandler,
+};
+use ghostnet_indexer::indexer::{
+    EventRouter, LogRouter, Quarantine, QuarantineRetrier, RetryReport,
+};
+use ghostnet_indexer::ports::{IndexerStateStore, PositionStore, QuarantineStore};
+use ghostnet_indexer::store::{MemoryCache, PostgresStore};
+use ghostnet_indexer::types::entities::RawLog;
+use ghostnet_indexer::types::enums::Level;
+use ghostnet_indexer::types::events::EventMetadata;
+use ghostnet_indexer::types::primitives::{BlockNumber, EthAddress, TokenAmount};
+
+const ALICE: Address = Address::repeat_byte(0x11);
+const BOB: Address = Address::repeat_byte(0x22);
+
+fn data(tokens: u64) -> U256 {
+    U256::from(tokens) * U256::from(10u64).pow(U256::from(18))
+}
+
+/// `event` as emitted at `log_index` of `block`.
+#[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+fn raw_log(event: &impl SolEvent, block: u64, log_index: u64) -> RawLog {
+    let meta = EventMetadata {
+        block_number: block,
+        block_hash: B256::left_padding_from(&block.to_be_bytes()),
+        tx_hash: B256::repeat_byte(log_index as u8 + 1),
+        tx_index: log_index,
+        log_index,
+        timestamp: DateTime::from_timestamp(1_700_000_000 + block as i64, 0).unwrap(),
+        contract: Address::ZERO,
+        chain_id: 6343,
+        tx_function: None,
+        tx_from: None,
+    };
+    let log = Log {
+        inner: alloy::primitives::Log {
+            address: Address::ZERO,
+            data: event.encode_log_data(),
+        },
+        ..Log::default()
+    };
+    RawLog::new(&log, &meta)
+}
+
+fn jacked_in(user: Address, tokens: u64, block: u64) -> RawLog {
+    let event = ghost_core::JackedIn {
+        user,
+        amount: data(tokens),
+        level: u8::from(Level::Mainframe),
+        newTotal: data(tokens),
+    };
+    raw_log(&event, block, 0)
+}
+
+fn stake_added(user: Address, tokens: u64, total: u64, block: u64) -> RawLog {
+    let event = ghost_core::StakeAdded {
+        user,
+        amount: data(tokens),
+        newTotal: data(total),
+    };
+    raw_log(&event, block, 0)
+}
+
+/// A router with every handler writing to `store`, quarantining through
+/// `quarantine` if given one.
+fn router(store: &Arc<PostgresStore>, quarantine: Option<Quarantine>) -> impl LogRouter + use<> {
+    let cache = Arc::new(MemoryCache::new());
+    EventRouter::new(
+        PositionHandler::new(store.clone(), cache.clone()),
+        ScanHandler::new(store.clone(), cache.clone()),
+        DeathHandler::new(store.clone(), store.clone(), cache.clone()),
+        MarketHandler::new(store.clone(), cache.clone()),
+        TokenHandler::new(cache.clone()),
+        FeeHandler::new(cache.clone()),
+        EmissionsHandler::new(cache),
+    )
+    .with_quarantine(quarantine)
+}
+
+/// A router quarantining into `db`, with the default settings.
+async fn quarantining_router(db: &TestDb) -> impl LogRouter + use<> {
+    let store = Arc::new(db.store.clone());
+    let quarantine = Quarantine::new(store.clone(), &QuarantineSettings::default());
+    quarantine.load().await.unwrap();
+    router(&store, Some(quarantine))
+}
+
+async fn active_amount(store: &PostgresStore, user: Address) -> Option<TokenAmount> {
+    store
+        .get_active_position(&EthAddress::new(user.into_array()))
+        .await
+        .unwrap()
+        .map(|position| position.amount)
+}
+
+/// Route Alice's stakes without her jacking in, and Bob jacking in between.
+async fn route_poisoned(db: &TestDb) {
+    let router = quarantining_router(db).await;
+    let logs = [
+        stake_added(ALICE, 50, 150, 11),
+        jacked_in(BOB, 30, 12),
+        stake_added(ALICE, 25, 175, 13),
+    ];
+
+    let first = router.route_log(&logs[0].log(), logs[0].metadata()).await;
+    assert!(matches!(first, Err(AppError::Quarantined(_))), "{first:?}");
+    let second = router.route_log(&logs[1].log(), logs[1].metadata()).await;
+    assert!(second.unwrap());
+    let blocked = router.route_log(&logs[2].log(), logs[2].metadata()).await;
+    assert!(!blocked.unwrap());
+}
+
+#[tokio::test]
+async fn test_poison_log_is_quarantined_and_blocks_its_user() {
+    let db = TestDb::new().await;
+    route_poisoned(&db).await;
+
+    let events = db.store.list_quarantined().await.unwrap();
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0].handler, "position");
+    assert_eq!(events[0].log.block_number, BlockNumber::new(11));
+    assert_eq!(events[0].aggregate, Some(ALICE.into_word()));
+    assert_eq!(events[0].blocked_by, None);
+    assert_eq!(events[1].log.block_number, BlockNumber::new(13));
+    assert_eq!(events[1].blocked_by, Some(events[0].id));
+    assert_eq!(db.store.quarantine_depth().await.unwrap(), 2);
+
+    // Bob was indexed regardless
+    assert_eq!(active_amount(&db.store, ALICE).await, None);
+    assert_eq!(
+        active_amount(&db.store, BOB).await,
+        Some(TokenAmount::from_wei(data(30), 18))
+    );
+
+    // A restarted router skips what is already quarantined
+    let router = quarantining_router(&db).await;
+    let raw = &events[0].log;
+    assert!(!router.route_log(&raw.log(), raw.metadata()).await.unwrap());
+    assert_eq!(db.store.quarantine_depth().await.unwrap(), 2);
+}
+
+#[tokio::test]
+async fn test_retry_releases_events_once_fixed() {
+    let db = TestDb::new().await;
+    route_poisoned(&db).await;
+    let store = Arc::new(db.store.batched());
+    let retrier = QuarantineRetrier::new(store.clone(), router(&store, None));
+
+    // Still no position: the first fails again and the second stays blocked
+    let report = retrier.retry_all().await.unwrap();
+    assert_eq!(
+        report,
+        RetryReport {
+            released: 0,
+            blocked: 1,
+            failed: 1,
+        }
+    );
+    let events = db.store.list_quarantined().await.unwrap();
+    assert_eq!(events[0].attempts, 1);
+
+    // The missed jack-in is indexed, e.g. by a backfill
+    let missed = jacked_in(ALICE, 100, 10);
+    let plain = Arc::new(db.store.clone());
+    router(&plain, None)
+        .route_log(&missed.log(), missed.metadata())
+        .await
+        .unwrap();
+
+    let report = retrier.retry_all().await.unwrap();
+    assert_eq!(report.released, 2);
+    assert!(db.store.list_quarantined().await.unwrap().is_empty());
+    assert_eq!(
+        active_amount(&db.store, ALICE).await,
+        Some(TokenAmount::from_wei(data(175), 18))
+    );
+}
+
+#[tokio::test]
+async fn test_reorg_drops_quarantined_events_past_the_fork() {
+    let db = TestDb::new().await;
+    route_poisoned(&db).await;
+
+    db.store
+        .execute_reorg_rollback(BlockNumber::new(12))
+        .await
+        .unwrap();
+
+    let events = db.store.list_quarantined().await.unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].log.block_number, BlockNumber::new(11));
+}
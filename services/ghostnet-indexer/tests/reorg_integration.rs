@@ -3,6 +3,9 @@
 //! These tests verify the full reorg detection and rollback flow
 //! using a real TimescaleDB instance.
 
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+#[allow(dead_code)] // Not every test binary uses every shared fixture
 mod common;
 
 use alloy::primitives::B256;
@@ -52,6 +55,7 @@ async fn test_reorg_detection_no_reorg() {
 }
 
 #[tokio::test]
+#[allow(clippy::panic)]
 async fn test_reorg_detection_parent_mismatch() {
     let db = TestDb::new().await;
 
@@ -130,6 +134,7 @@ async fn test_reorg_detection_parent_not_found() {
 }
 
 #[tokio::test]
+#[allow(clippy::cast_possible_truncation)]
 async fn test_rollback_clears_blocks_after_fork_point() {
     let db = TestDb::new().await;
 
@@ -425,6 +430,7 @@ async fn test_checkpoint_reset_after_rollback() {
 // ═══════════════════════════════════════════════════════════════════════════════
 
 #[tokio::test]
+#[allow(clippy::panic)]
 async fn test_full_reorg_workflow_with_positions() {
     let db = TestDb::new().await;
 
@@ -0,0 +1,241 @@
+//! Integration tests for archiving raw logs and replaying them.
+//!
+//! These tests archive canned `GhostCore` logs in a real TimescaleDB instance
+//! in Docker, corrupt the tables derived from them, then check that replaying
+//! the archive through the handlers rebuilds what was indexed.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+#[allow(dead_code)] // Not every test binary uses every shared fixture
+mod common;
+
+use std::sync::Arc;
+
+use alloy::primitives::{Address, B256, U256};
+use alloy::rpc::types::Log;
+use alloy::sol_types::SolEvent;
+use chrono::DateTime;
+
+use common::fixtures::TestDb;
+use ghostnet_indexer::abi::ghost_core;
+use ghostnet_indexer::handlers::{
+    DeathHandler, EmissionsHandler, FeeHandler, MarketHandler, PositionHandler, ScanHandler,
+    TokenHandler,
+};
+use ghostnet_indexer::indexer::{EventRouter, LogReplayer, LogRouter};
+use ghostnet_indexer::ports::{IndexerStateStore, PositionStore, RawLogStore};
+use ghostnet_indexer::store::{MemoryCache, PostgresStore};
+use ghostnet_indexer::types::entities::RawLog;
+use ghostnet_indexer::types::enums::Level;
+use ghostnet_indexer::types::events::EventMetadata;
+use ghostnet_indexer::types::primitives::{BlockNumber, EthAddress, TokenAmount};
+
+const ALICE: Address = Address::repeat_byte(0x11);
+const BOB: Address = Address::repeat_byte(0x22);
+
+fn data(tokens: u64) -> U256 {
+    U256::from(tokens) * U256::from(10u64).pow(U256::from(18))
+}
+
+/// The archive entry of `event`, emitted at `log_index` of `block`.
+#[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+fn raw_log(event: &impl SolEvent, block: u64, log_index: u64) -> RawLog {
+    let meta = EventMetadata {
+        block_number: block,
+        block_hash: B256::left_padding_from(&block.to_be_bytes()),
+        tx_hash: B256::repeat_byte(log_index as u8 + 1),
+        tx_index: log_index,
+        log_index,
+        timestamp: DateTime::from_timestamp(1_700_000_000 + block as i64, 0).unwrap(),
+        contract: Address::ZERO,
+        chain_id: 6343,
+        tx_function: None,
+        tx_from: None,
+    };
+    let log = Log {
+        inner: alloy::primitives::Log {
+            address: Address::ZERO,
+            data: event.encode_log_data(),
+        },
+        ..Log::default()
+    };
+    RawLog::new(&log, &meta)
+}
+
+/// Alice jacks in and adds to her stake, Bob jacks in.
+fn archive() -> Vec<RawLog> {
+    vec![
+        raw_log(
+            &ghost_core::JackedIn {
+                user: ALICE,
+                amount: data(100),
+                level: u8::from(Level::Mainframe),
+                newTotal: data(100),
+            },
+            10,
+            0,
+        ),
+        raw_log(
+            &ghost_core::StakeAdded {
+                user: ALICE,
+                amount: data(50),
+                newTotal: data(150),
+            },
+            11,
+            0,
+        ),
+        raw_log(
+            &ghost_core::JackedIn {
+                user: BOB,
+                amount: data(30),
+                level: u8::from(Level::Subnet),
+                newTotal: data(30),
+            },
+            11,
+            1,
+        ),
+    ]
+}
+
+/// A router with every handler writing to `store`, without the outbox.
+fn router(store: &Arc<PostgresStore>) -> impl LogRouter + use<> {
+    let cache = Arc::new(MemoryCache::new());
+    EventRouter::new(
+        PositionHandler::new(store.clone(), cache.clone()),
+        ScanHandler::new(store.clone(), cache.clone()),
+        DeathHandler::new(store.clone(), store.clone(), cache.clone()),
+        MarketHandler::new(store.clone(), cache.clone()),
+        TokenHandler::new(cache.clone()),
+        FeeHandler::new(cache.clone()),
+        EmissionsHandler::new(cache),
+    )
+}
+
+/// Index the archive as the pipeline would: archive each log, then route it.
+async fn index(db: &TestDb) {
+    let store = Arc::new(db.store.clone());
+    let router = router(&store);
+    for raw in archive() {
+        store.archive_logs(std::slice::from_ref(&raw)).await.unwrap();
+        router.route_log(&raw.log(), raw.metadata()).await.unwrap();
+    }
+}
+
+async fn active_amount(store: &PostgresStore, user: Address) -> Option<TokenAmount> {
+    store
+        .get_active_position(&EthAddress::new(user.into_array()))
+        .await
+        .unwrap()
+        .map(|position| position.amount)
+}
+
+#[tokio::test]
+async fn test_raw_logs_read_back_in_pages_and_roll_back() {
+    let db = TestDb::new().await;
+    let mut logs = archive();
+    logs.reverse();
+    db.store.archive_logs(&logs).await.unwrap();
+    // Archiving again is a no-op
+    db.store.archive_logs(&logs[..1]).await.unwrap();
+
+    let (from, to) = (BlockNumber::new(0), BlockNumber::new(100));
+    let first = db.store.get_raw_logs(from, to, None, 2).await.unwrap();
+    let last = &first[1];
+    let after = Some((last.block_number, last.tx_index, last.log_index));
+    let rest = db.store.get_raw_logs(from, to, after, 2).await.unwrap();
+
+    let read: Vec<_> = first.into_iter().chain(rest).collect();
+    assert_eq!(read, archive());
+    assert_eq!(
+        db.store.raw_log_range().await.unwrap(),
+        Some((BlockNumber::new(10), BlockNumber::new(11)))
+    );
+
+    db.store
+        .execute_reorg_rollback(BlockNumber::new(10))
+        .await
+        .unwrap();
+    assert_eq!(
+        db.store.raw_log_range().await.unwrap(),
+        Some((BlockNumber::new(10), BlockNumber::new(10)))
+    );
+}
+
+#[tokio::test]
+async fn test_replay_rebuilds_corrupted_positions() {
+    let db = TestDb::new().await;
+    index(&db).await;
+    assert_eq!(
+        active_amount(&db.store, ALICE).await,
+        Some(TokenAmount::from_wei(data(150), 18))
+    );
+
+    // Corrupt the derived tables: a wrong stake, and a lost position
+    sqlx::query("UPDATE positions SET amount = 1 WHERE user_address = $1")
+        .bind(ALICE.as_slice())
+        .execute(&db.pool)
+        .await
+        .unwrap();
+    sqlx::query("DELETE FROM positions WHERE user_address = $1")
+        .bind(BOB.as_slice())
+        .execute(&db.pool)
+        .await
+        .unwrap();
+
+    let batched = Arc::new(db.store.batched());
+    let replayer = LogReplayer::new(batched.clone(), router(&batched), &["position", "death"])
+        .unwrap()
+        .with_truncate_derived(true);
+    let report = replayer
+        .replay(BlockNumber::new(10), BlockNumber::new(11))
+        .await
+        .unwrap();
+
+    assert!(report.complete);
+    assert_eq!((report.routed, report.failed), (3, 0));
+    assert_eq!(
+        active_amount(&db.store, ALICE).await,
+        Some(TokenAmount::from_wei(data(150), 18))
+    );
+    assert_eq!(
+        active_amount(&db.store, BOB).await,
+        Some(TokenAmount::from_wei(data(30), 18))
+    );
+    let history: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM position_history")
+        .fetch_one(&db.pool)
+        .await
+        .unwrap();
+    assert_eq!(history, 3);
+    assert_eq!(
+        db.store
+            .get_replay_progress("10-11:death,position")
+            .await
+            .unwrap(),
+        None
+    );
+}
+
+#[tokio::test]
+async fn test_replay_runs_only_named_handlers() {
+    let db = TestDb::new().await;
+    index(&db).await;
+    sqlx::query("UPDATE positions SET amount = 1")
+        .execute(&db.pool)
+        .await
+        .unwrap();
+
+    let batched = Arc::new(db.store.batched());
+    let replayer =
+        LogReplayer::new(batched.clone(), router(&batched), &["scan", "market"]).unwrap();
+    let report = replayer
+        .replay(BlockNumber::new(10), BlockNumber::new(11))
+        .await
+        .unwrap();
+
+    // The position logs were skipped, leaving the positions as they were
+    assert_eq!((report.routed, report.skipped), (0, 3));
+    assert_ne!(
+        active_amount(&db.store, ALICE).await,
+        Some(TokenAmount::from_wei(data(150), 18))
+    );
+}
@@ -0,0 +1,188 @@
+//! Integration tests for riding out database outages.
+//!
+//! These tests stop and restart a real TimescaleDB container in Docker
+//! while the store is in use, connecting through a [`DbProxy`] that follows
+//! the container to its new port.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+#[allow(dead_code)] // Not every test binary uses every shared fixture
+mod common;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use alloy::primitives::{Address, B256};
+use alloy::rpc::types::Log;
+use async_trait::async_trait;
+use chrono::Utc;
+use sqlx::postgres::PgPoolOptions;
+use testcontainers::ContainerAsync;
+use testcontainers::runners::AsyncRunner;
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+
+use common::containers::{TimescaleDb, build_connection_string};
+use common::proxy::DbProxy;
+use ghostnet_indexer::error::Result;
+use ghostnet_indexer::indexer::{CheckpointManager, Ingest, LogRouter, Pipeline};
+use ghostnet_indexer::ports::{IndexerStateStore, RawLogStore};
+use ghostnet_indexer::store::{PostgresStore, RetryPolicy};
+use ghostnet_indexer::types::events::EventMetadata;
+use ghostnet_indexer::types::primitives::BlockNumber;
+
+/// A migrated database whose container the test can stop and restart.
+struct RestartableDb {
+    store: PostgresStore,
+    proxy: DbProxy,
+    container: ContainerAsync<TimescaleDb>,
+}
+
+impl RestartableDb {
+    async fn new() -> Self {
+        let container = TimescaleDb::default()
+            .start()
+            .await
+            .expect("Failed to start TimescaleDB container");
+        let proxy = DbProxy::start(container.get_host_port_ipv4(5432).await.unwrap()).await;
+
+        let url = build_connection_string("127.0.0.1", proxy.port());
+        let pool = PgPoolOptions::new()
+            .max_connections(4)
+            .acquire_timeout(Duration::from_secs(2))
+            .connect(&url)
+            .await
+            .expect("Failed to connect to database");
+        let store = PostgresStore::new(pool)
+            .with_retry_policy(RetryPolicy::new(20, Duration::from_millis(250)));
+        store.run_migrations().await.expect("Failed to run migrations");
+
+        Self {
+            store,
+            proxy,
+            container,
+        }
+    }
+
+    /// Stop the container, as if the database crashed.
+    async fn kill(&self) {
+        self.container.stop().await.expect("Failed to stop container");
+    }
+
+    /// Start the container again and point the proxy at its new port.
+    async fn restart(&self) {
+        self.container.start().await.expect("Failed to start container");
+        let port = self.container.get_host_port_ipv4(5432).await.unwrap();
+        self.proxy.set_upstream(port);
+    }
+}
+
+/// Router that handles nothing; the archive does the writing.
+#[derive(Debug)]
+struct NoopRouter;
+
+#[async_trait]
+impl LogRouter for NoopRouter {
+    async fn route_log(&self, _log: &Log, _meta: EventMetadata) -> Result<bool> {
+        Ok(true)
+    }
+}
+
+fn log_at(block: u64) -> Ingest {
+    let meta = EventMetadata {
+        block_number: block,
+        block_hash: B256::left_padding_from(&block.to_be_bytes()),
+        tx_hash: B256::ZERO,
+        tx_index: 0,
+        log_index: 0,
+        timestamp: Utc::now(),
+        contract: Address::ZERO,
+        chain_id: 6343,
+        tx_function: None,
+        tx_from: None,
+    };
+    Ingest::Log(Log::default(), meta)
+}
+
+#[tokio::test]
+async fn test_store_retries_connections_across_restart() {
+    let db = RestartableDb::new().await;
+    db.store
+        .set_last_block(BlockNumber::new(7), B256::repeat_byte(7))
+        .await
+        .unwrap();
+
+    db.kill().await;
+    let health = db.store.health().await;
+    assert!(!health.is_healthy());
+    assert!(health.error.is_some());
+
+    // The query waits out the restart instead of failing
+    let restart = async {
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        db.restart().await;
+    };
+    let (last, ()) = tokio::join!(db.store.get_last_block(), restart);
+    assert_eq!(last.unwrap(), BlockNumber::new(7));
+
+    let health = db.store.health().await;
+    assert!(health.is_healthy(), "{health:?}");
+    assert_eq!(health.in_recovery, Some(false));
+}
+
+#[tokio::test]
+async fn test_pipeline_pauses_and_resumes_across_restart() {
+    let db = RestartableDb::new().await;
+    let writer = db.store.batched();
+    let archive: Arc<dyn RawLogStore> = Arc::new(writer.clone());
+    let pipeline = Pipeline::new(NoopRouter, CheckpointManager::new(writer))
+        .with_batch_window(Some(Duration::ZERO))
+        .with_archive(Some(archive));
+
+    let (tx, rx) = mpsc::channel(4);
+    let shutdown = CancellationToken::new();
+    let run = tokio::spawn({
+        let shutdown = shutdown.clone();
+        async move { pipeline.run(rx, shutdown).await }
+    });
+
+    for block in 1..=10 {
+        tx.send(log_at(block)).await.unwrap();
+    }
+    tokio::time::sleep(Duration::from_secs(1)).await;
+
+    // Mid-ingestion the database goes away; the pipeline stops taking logs
+    // once the channel is full, and takes them again after the restart
+    db.kill().await;
+    let producer = tokio::spawn(async move {
+        for block in 11..=20 {
+            tx.send(log_at(block)).await.unwrap();
+        }
+        tx.send(Ingest::BlocksComplete {
+            through: 20,
+            hash: B256::left_padding_from(&20u64.to_be_bytes()),
+        })
+        .await
+        .unwrap();
+    });
+    tokio::time::sleep(Duration::from_secs(3)).await;
+    db.restart().await;
+    producer.await.unwrap();
+
+    let last = run.await.unwrap().unwrap();
+    assert_eq!(last, Some(BlockNumber::new(20)));
+    assert_eq!(
+        db.store.get_last_block().await.unwrap(),
+        BlockNumber::new(20)
+    );
+
+    // No log was skipped or archived twice
+    let logs = db
+        .store
+        .get_raw_logs(BlockNumber::new(1), BlockNumber::new(20), None, 100)
+        .await
+        .unwrap();
+    let blocks: Vec<u64> = logs.iter().map(|log| log.block_number.value()).collect();
+    assert_eq!(blocks, (1..=20).collect::<Vec<_>>());
+    shutdown.cancel();
+}
@@ -4,15 +4,36 @@
 //! They verify that our store implementations work correctly with
 //! the actual database schema and TimescaleDB extensions.
 
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+#[allow(dead_code)] // Not every test binary uses every shared fixture
 mod common;
 
-use alloy::primitives::{B256, U256};
+use std::sync::Arc;
+use std::time::Duration;
 
-use common::fixtures::{TestDb, death_fixtures, position_fixtures, scan_fixtures};
-use ghostnet_indexer::ports::{DeathStore, IndexerStateStore, PositionStore, ScanStore};
-use ghostnet_indexer::types::entities::ScanFinalizationData;
-use ghostnet_indexer::types::enums::Level;
-use ghostnet_indexer::types::primitives::{BlockNumber, TokenAmount};
+use alloy::primitives::{B256, U256};
+use chrono::DurationRound;
+use uuid::Uuid;
+
+use common::fixtures::{
+    TestDb, death_fixtures, market_fixtures, position_fixtures, scan_fixtures,
+};
+use ghostnet_indexer::config::LeaderboardSettings;
+use ghostnet_indexer::indexer::{Contract, LeaderboardRefresher};
+use ghostnet_indexer::ports::{
+    BoostStore, Cache, DeathStore, EventOutboxStore, HolderStore, IndexerStateStore,
+    LeaderboardStore, MarketStore, PositionStore, ScanStore, StatsStore, TokenFlowStore,
+};
+use ghostnet_indexer::store::{MemoryCache, PostgresStore};
+use ghostnet_indexer::types::entities::{
+    AddressFlowDelta, Boost, CascadeShare, EffectiveStakeChange, HistoryBucket, HistoryCursor,
+    HolderBalance, HolderSort, LeaderboardEntry, LevelHistoryPoint, LevelScanStats, OutboxEvent,
+    Position, PositionAction, PositionFilter, PositionHistoryEntry, RoundSettlement, Scan,
+    ScanFinalizationData, TokenFlowDelta, TokenTransfer,
+};
+use ghostnet_indexer::types::enums::{BoostType, ExitReason, LeaderboardType, Level};
+use ghostnet_indexer::types::primitives::{BlockNumber, EthAddress, GhostStreak, TokenAmount};
 
 // ═══════════════════════════════════════════════════════════════════════════════
 // POSITION STORE TESTS
@@ -116,6 +137,202 @@ async fn test_get_positions_by_level() {
     assert_eq!(count, 2);
 }
 
+#[tokio::test]
+async fn test_position_history_pages_newest_first() {
+    let db = TestDb::new().await;
+
+    let mut position = position_fixtures::create_test_position(
+        "0x7777777777777777777777777777777777777777",
+        Level::Subnet,
+    );
+    let start = position.entry_timestamp;
+    let mut ids = Vec::new();
+    for i in 0..5 {
+        let action = if i == 0 {
+            PositionAction::JackedIn
+        } else {
+            PositionAction::StakeAdded
+        };
+        let entry = PositionHistoryEntry::new(
+            &position,
+            action,
+            position.amount.clone(),
+            BlockNumber::new(100 + i),
+            start + chrono::Duration::seconds(i.cast_signed()),
+        );
+        db.store
+            .save_position_with_history(&position, &entry, &[])
+            .await
+            .unwrap();
+        ids.push(entry.id);
+        position.ghost_streak = GhostStreak::new(i32::try_from(i).unwrap() + 1).unwrap();
+    }
+
+    let first = db
+        .store
+        .get_history(&position.user_address, 3, None)
+        .await
+        .unwrap();
+    let cursor = HistoryCursor::of(first.last().unwrap());
+    let rest = db
+        .store
+        .get_history(&position.user_address, 3, Some(cursor))
+        .await
+        .unwrap();
+
+    let paged: Vec<_> = first.iter().chain(&rest).map(|e| e.id).collect();
+    ids.reverse();
+    assert_eq!(paged, ids);
+    assert_eq!(first[0].ghost_streak, GhostStreak::new(4).unwrap());
+    assert_eq!(first[0].level, Level::Subnet);
+}
+
+/// Save `count` positions with a spread of levels, stakes, streaks and entry
+/// times, every seventh one dead. Pairs of positions share an entry time so
+/// paging has to break ties by ID.
+#[allow(clippy::cast_possible_truncation)]
+async fn seed_positions(store: &PostgresStore, count: u64) -> Vec<Position> {
+    let start = chrono::DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+    let levels = [
+        Level::Vault,
+        Level::Mainframe,
+        Level::Subnet,
+        Level::Darknet,
+        Level::BlackIce,
+    ];
+    let mut positions = Vec::new();
+    for i in 0..count {
+        let user = format!("0x{:040x}", i + 1);
+        let level = levels[i as usize % levels.len()];
+        let mut position = if i % 7 == 0 {
+            position_fixtures::create_dead_position(&user, level, ExitReason::Traced)
+        } else {
+            position_fixtures::create_test_position(&user, level)
+        };
+        position.amount = TokenAmount::parse(&((i % 50) + 1).to_string()).unwrap();
+        position.ghost_streak = GhostStreak::new(i32::try_from(i % 10).unwrap()).unwrap();
+        position.entry_timestamp = start + chrono::Duration::seconds((i / 2).cast_signed());
+        store.save_position(&position).await.unwrap();
+        positions.push(position);
+    }
+    positions
+}
+
+/// Every page of `filter`, following `next_cursor` until it runs out.
+async fn query_all(store: &PostgresStore, filter: &PositionFilter, limit: u32) -> Vec<Uuid> {
+    let mut filter = filter.clone();
+    let mut ids = Vec::new();
+    loop {
+        let page = store.query(&filter, limit).await.unwrap();
+        assert!(page.items.len() <= limit as usize);
+        ids.extend(page.items.iter().map(|p| p.id));
+        match page.next_cursor {
+            Some(cursor) => filter.after = Some(cursor.parse().unwrap()),
+            None => return ids,
+        }
+    }
+}
+
+/// The IDs of `positions` matching `filter`, in listing order.
+fn expected_ids(positions: &[Position], filter: &PositionFilter) -> Vec<Uuid> {
+    let mut matching: Vec<_> = positions
+        .iter()
+        .filter(|p| filter.level.is_none_or(|level| p.level == level))
+        .filter(|p| filter.min_amount.as_ref().is_none_or(|min| p.amount >= *min))
+        .filter(|p| filter.max_amount.as_ref().is_none_or(|max| p.amount <= *max))
+        .filter(|p| filter.min_streak.is_none_or(|min| p.ghost_streak >= min))
+        .filter(|p| !filter.active_only || p.is_active())
+        .filter(|p| filter.created_after.is_none_or(|t| p.entry_timestamp > t))
+        .collect();
+    matching.sort_by_key(|p| std::cmp::Reverse((p.entry_timestamp, p.id)));
+    matching.iter().map(|p| p.id).collect()
+}
+
+// EXPLAIN for the listing queries should show idx_positions_entry_id or one of
+// its level and active variants rather than a sequential scan; that needs a
+// realistically sized table, so it is checked by hand rather than here.
+#[tokio::test]
+async fn test_position_query_filters() {
+    let db = TestDb::new().await;
+    let positions = seed_positions(&db.store, 300).await;
+    let start = positions[0].entry_timestamp;
+
+    let filters = [
+        PositionFilter::default(),
+        PositionFilter {
+            level: Some(Level::Darknet),
+            ..PositionFilter::default()
+        },
+        PositionFilter {
+            min_amount: Some(TokenAmount::parse("20").unwrap()),
+            max_amount: Some(TokenAmount::parse("30").unwrap()),
+            ..PositionFilter::default()
+        },
+        PositionFilter {
+            min_streak: Some(GhostStreak::new(7).unwrap()),
+            ..PositionFilter::default()
+        },
+        PositionFilter {
+            active_only: true,
+            ..PositionFilter::default()
+        },
+        PositionFilter {
+            created_after: Some(start + chrono::Duration::seconds(100)),
+            ..PositionFilter::default()
+        },
+        PositionFilter {
+            level: Some(Level::BlackIce),
+            min_amount: Some(TokenAmount::parse("10").unwrap()),
+            min_streak: Some(GhostStreak::new(2).unwrap()),
+            active_only: true,
+            ..PositionFilter::default()
+        },
+    ];
+    for filter in &filters {
+        let expected = expected_ids(&positions, filter);
+        assert!(!expected.is_empty(), "{filter:?} matches nothing");
+        assert_eq!(query_all(&db.store, filter, 25).await, expected, "{filter:?}");
+    }
+
+    // A page that ends exactly at the last match has no next cursor
+    let vault = PositionFilter {
+        level: Some(Level::Vault),
+        ..PositionFilter::default()
+    };
+    let page = db.store.query(&vault, 60).await.unwrap();
+    assert_eq!(page.items.len(), 60);
+    assert_eq!(page.next_cursor, None);
+}
+
+#[tokio::test]
+async fn test_position_query_pages_are_stable_under_inserts() {
+    let db = TestDb::new().await;
+    let positions = seed_positions(&db.store, 200).await;
+    let filter = PositionFilter::default();
+
+    let first = db.store.query(&filter, 50).await.unwrap();
+    let mut ids: Vec<_> = first.items.iter().map(|p| p.id).collect();
+
+    // Positions entered after the first page was read sort before its
+    // cursor, so they must not shift or repeat anything in later pages
+    let newest = positions.last().unwrap().entry_timestamp;
+    for i in 0..20 {
+        let mut position = position_fixtures::create_test_position(
+            &format!("0x{:040x}", 10_000 + i),
+            Level::Subnet,
+        );
+        position.entry_timestamp = newest + chrono::Duration::seconds(i + 1);
+        db.store.save_position(&position).await.unwrap();
+    }
+
+    let rest = PositionFilter {
+        after: Some(first.next_cursor.unwrap().parse().unwrap()),
+        ..filter
+    };
+    ids.extend(query_all(&db.store, &rest, 50).await);
+    assert_eq!(ids, expected_ids(&positions, &PositionFilter::default()));
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // SCAN STORE TESTS
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -159,6 +376,8 @@ async fn test_scan_finalize() {
         distributed_upstream: TokenAmount::from_wei(U256::from(1_500_000_000_000_000_000u128), 18),
         protocol_fee: TokenAmount::from_wei(U256::from(1_000_000_000_000_000_000u128), 18),
         survivor_count: 95,
+        finalized_block: BlockNumber::new(1_000),
+        finalization_latency_ms: Some(60_000),
     };
     db.store
         .finalize_scan(&scan.scan_id, finalization_data)
@@ -176,6 +395,33 @@ async fn test_scan_finalize() {
     assert!(retrieved.finalized_at.is_some());
     assert_eq!(retrieved.death_count, Some(5));
     assert_eq!(retrieved.survivor_count, Some(95));
+    assert_eq!(retrieved.finalized_block, Some(BlockNumber::new(1_000)));
+    assert_eq!(retrieved.finalization_latency_ms, Some(60_000));
+}
+
+#[tokio::test]
+async fn test_save_scan_replaces_placeholder() {
+    let db = TestDb::new().await;
+
+    let placeholder = Scan::placeholder("placeholder-1".into(), Level::Darknet, chrono::Utc::now());
+    db.store.save_scan(&placeholder).await.unwrap();
+
+    let mut scan = scan_fixtures::create_pending_scan(Level::Darknet);
+    scan.id = placeholder.id;
+    scan.scan_id = placeholder.scan_id.clone();
+    db.store.save_scan(&scan).await.unwrap();
+
+    // A complete scan is not overwritten by a later placeholder
+    db.store.save_scan(&placeholder).await.unwrap();
+
+    let retrieved = db
+        .store
+        .get_scan_by_id(&scan.scan_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(retrieved.seed, scan.seed);
+    assert!(!retrieved.is_placeholder());
 }
 
 #[tokio::test]
@@ -314,6 +560,112 @@ async fn test_death_linked_to_scan() {
     assert_eq!(deaths[0].position_id, Some(position.id));
 }
 
+#[tokio::test]
+async fn test_link_deaths_to_scan() {
+    let db = TestDb::new().await;
+
+    let scan = scan_fixtures::create_pending_scan(Level::Subnet);
+    db.store.save_scan(&scan).await.unwrap();
+
+    let deaths = vec![
+        death_fixtures::create_test_death(
+            "0x1111111111111111111111111111111111111111",
+            Level::Subnet,
+            1_000_000_000_000_000_000,
+        ),
+        death_fixtures::create_test_death(
+            "0x2222222222222222222222222222222222222222",
+            Level::Subnet,
+            2_000_000_000_000_000_000,
+        ),
+    ];
+    db.store.record_deaths(&deaths).await.unwrap();
+
+    let ids: Vec<_> = deaths.iter().map(|d| d.id).collect();
+    let linked = db
+        .store
+        .link_deaths_to_scan(&scan.scan_id, &ids)
+        .await
+        .unwrap();
+    assert_eq!(linked, 2);
+
+    let deaths = db.store.get_deaths_for_scan(&scan.scan_id).await.unwrap();
+    assert_eq!(deaths.len(), 2);
+    assert!(deaths.iter().all(|d| d.scan_id == Some(scan.id)));
+
+    // Linking to an unknown scan fails
+    assert!(db.store.link_deaths_to_scan("missing", &ids).await.is_err());
+}
+
+#[tokio::test]
+async fn test_cascade_earnings_per_scan() {
+    let db = TestDb::new().await;
+
+    let mut scan = scan_fixtures::create_finalized_scan(Level::Darknet, 1);
+    scan.finalized_block = Some(BlockNumber::new(500));
+    db.store.save_scan(&scan).await.unwrap();
+
+    let survivor = position_fixtures::create_test_position(
+        "0x1111111111111111111111111111111111111111",
+        Level::Darknet,
+    );
+    let upstream = position_fixtures::create_test_position(
+        "0x2222222222222222222222222222222222222222",
+        Level::Vault,
+    );
+    let share = |position: &Position, amount: u128, block: u64| CascadeShare {
+        id: uuid::Uuid::new_v4(),
+        position_id: position.id,
+        user_address: position.user_address,
+        source_level: Level::Darknet,
+        level: position.level,
+        amount: tokens(amount),
+        block_number: BlockNumber::new(block),
+        created_at: chrono::Utc::now(),
+    };
+    let shares = [
+        share(&survivor, 30, 500),
+        share(&upstream, 10, 500),
+        // The finalization of this scan has not been indexed
+        share(&survivor, 5, 580),
+    ];
+    db.store.record_cascade_shares(&shares).await.unwrap();
+    // Replays are ignored
+    db.store.record_cascade_shares(&shares).await.unwrap();
+
+    let earnings = db
+        .store
+        .get_cascade_earnings(&survivor.user_address, 10)
+        .await
+        .unwrap();
+    assert_eq!(earnings.total, tokens(35));
+    let scans: Vec<_> = earnings
+        .scans
+        .iter()
+        .map(|s| (s.scan_id.clone(), s.block_number.value(), s.amount.clone()))
+        .collect();
+    assert_eq!(
+        scans,
+        [
+            (None, 580, tokens(5)),
+            (Some(scan.scan_id.clone()), 500, tokens(30)),
+        ]
+    );
+
+    let entries = db
+        .store
+        .get_leaderboard(LeaderboardType::CascadeEarnings, 10)
+        .await
+        .unwrap();
+    assert_eq!(
+        ranking(&entries),
+        [
+            (1, survivor.user_address.to_hex(), tokens(35)),
+            (2, upstream.user_address.to_hex(), tokens(10)),
+        ]
+    );
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // INDEXER STATE STORE TESTS
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -362,6 +714,7 @@ async fn test_block_hash_storage() {
 }
 
 #[tokio::test]
+#[allow(clippy::cast_possible_truncation)]
 async fn test_reorg_rollback() {
     let db = TestDb::new().await;
 
@@ -419,6 +772,7 @@ async fn test_reorg_rollback() {
 }
 
 #[tokio::test]
+#[allow(clippy::cast_possible_truncation)]
 async fn test_prune_old_blocks() {
     let db = TestDb::new().await;
 
@@ -462,6 +816,974 @@ async fn test_prune_old_blocks() {
     );
 }
 
+#[tokio::test]
+async fn test_contract_cursors() {
+    let db = TestDb::new().await;
+    let all = [Contract::GhostCore, Contract::DeadPool];
+
+    assert_eq!(db.store.get_cursor(Contract::DeadPool).await.unwrap(), None);
+    assert_eq!(db.store.min_cursor(&[]).await.unwrap(), None);
+
+    db.store
+        .set_cursor(Contract::GhostCore, BlockNumber::new(200))
+        .await
+        .unwrap();
+    // A contract without a cursor counts as block 0
+    assert_eq!(
+        db.store.min_cursor(&all).await.unwrap(),
+        Some(BlockNumber::new(0))
+    );
+
+    db.store
+        .set_cursor(Contract::DeadPool, BlockNumber::new(150))
+        .await
+        .unwrap();
+    assert_eq!(
+        db.store.get_cursor(Contract::DeadPool).await.unwrap(),
+        Some(BlockNumber::new(150))
+    );
+    assert_eq!(
+        db.store.min_cursor(&all).await.unwrap(),
+        Some(BlockNumber::new(150))
+    );
+
+    // Rollback moves cursors past the fork point back to it
+    db.store
+        .execute_reorg_rollback(BlockNumber::new(180))
+        .await
+        .unwrap();
+    assert_eq!(
+        db.store.get_cursor(Contract::GhostCore).await.unwrap(),
+        Some(BlockNumber::new(180))
+    );
+    assert_eq!(
+        db.store.get_cursor(Contract::DeadPool).await.unwrap(),
+        Some(BlockNumber::new(150))
+    );
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// LEADERBOARD STORE TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+fn tokens(n: u128) -> TokenAmount {
+    TokenAmount::from_wei(U256::from(n * 1_000_000_000_000_000_000u128), 18)
+}
+
+/// Seed alive positions with streaks and extracted positions with amounts.
+async fn seed_leaderboards(db: &TestDb) {
+    for (user, streak) in [
+        ("0x3333333333333333333333333333333333333333", 5),
+        ("0x1111111111111111111111111111111111111111", 9),
+        ("0x2222222222222222222222222222222222222222", 9),
+        ("0x4444444444444444444444444444444444444444", 1),
+    ] {
+        let mut position = position_fixtures::create_test_position(user, Level::Subnet);
+        position.ghost_streak = GhostStreak::new(streak).unwrap();
+        db.store.save_position(&position).await.unwrap();
+    }
+
+    // Dead positions never rank on the streak leaderboard
+    let mut dead = position_fixtures::create_dead_position(
+        "0x5555555555555555555555555555555555555555",
+        Level::Darknet,
+        ExitReason::Traced,
+    );
+    dead.ghost_streak = GhostStreak::new(50).unwrap();
+    db.store.save_position(&dead).await.unwrap();
+
+    for (user, amount) in [
+        ("0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa", 100),
+        ("0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa", 50),
+        ("0xbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb", 120),
+        ("0xcccccccccccccccccccccccccccccccccccccccc", 150),
+        ("0x9999999999999999999999999999999999999999", 120),
+    ] {
+        let mut position = position_fixtures::create_extracted_position(user, Level::Mainframe);
+        position.extracted_amount = Some(tokens(amount));
+        db.store.save_position(&position).await.unwrap();
+    }
+}
+
+fn streak(n: u32) -> TokenAmount {
+    TokenAmount::parse(&n.to_string()).unwrap()
+}
+
+fn ranking(entries: &[LeaderboardEntry]) -> Vec<(u32, String, TokenAmount)> {
+    entries
+        .iter()
+        .map(|e| (e.rank, e.user_address.to_hex(), e.score.clone()))
+        .collect()
+}
+
+#[tokio::test]
+async fn test_ghost_streak_leaderboard() {
+    let db = TestDb::new().await;
+    seed_leaderboards(&db).await;
+
+    let entries = db
+        .store
+        .get_leaderboard(LeaderboardType::GhostStreak, 3)
+        .await
+        .unwrap();
+
+    // Tied streaks are ordered by address
+    assert_eq!(
+        ranking(&entries),
+        [
+            (1, "0x1111111111111111111111111111111111111111".into(), streak(9)),
+            (2, "0x2222222222222222222222222222222222222222".into(), streak(9)),
+            (3, "0x3333333333333333333333333333333333333333".into(), streak(5)),
+        ]
+    );
+    assert_eq!(entries[0].metadata, Some(serde_json::json!({ "level": 3 })));
+}
+
+#[tokio::test]
+async fn test_total_extracted_leaderboard() {
+    let db = TestDb::new().await;
+    seed_leaderboards(&db).await;
+
+    let entries = db
+        .store
+        .get_leaderboard(LeaderboardType::TotalExtracted, 10)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        ranking(&entries),
+        [
+            (1, "0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".into(), tokens(150)),
+            (2, "0xcccccccccccccccccccccccccccccccccccccccc".into(), tokens(150)),
+            (3, "0x9999999999999999999999999999999999999999".into(), tokens(120)),
+            (4, "0xbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb".into(), tokens(120)),
+        ]
+    );
+    assert_eq!(entries[0].metadata, Some(serde_json::json!({ "extractions": 2 })));
+}
+
+#[tokio::test]
+async fn test_biggest_extraction_leaderboard() {
+    let db = TestDb::new().await;
+    seed_leaderboards(&db).await;
+
+    let entries = db
+        .store
+        .get_leaderboard(LeaderboardType::BiggestExtraction, 10)
+        .await
+        .unwrap();
+
+    // One entry per wallet, with its largest extraction
+    assert_eq!(
+        ranking(&entries),
+        [
+            (1, "0xcccccccccccccccccccccccccccccccccccccccc".into(), tokens(150)),
+            (2, "0x9999999999999999999999999999999999999999".into(), tokens(120)),
+            (3, "0xbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb".into(), tokens(120)),
+            (4, "0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".into(), tokens(100)),
+        ]
+    );
+}
+
+/// Seed two resolved rounds and an open one, with bets won, lost and
+/// claimed early.
+async fn seed_dead_pool(db: &TestDb) {
+    const ALICE: &str = "0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+    const BOB: &str = "0xbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb";
+    const CAROL: &str = "0xcccccccccccccccccccccccccccccccccccccccc";
+    const DAVE: &str = "0xdddddddddddddddddddddddddddddddddddddddd";
+
+    let first = market_fixtures::create_test_round("1");
+    let second = market_fixtures::create_test_round("2");
+    let open = market_fixtures::create_test_round("3");
+    for (round, bets) in [
+        (&first, vec![(ALICE, true, 20), (BOB, true, 40), (CAROL, false, 60)]),
+        (&second, vec![(ALICE, false, 30), (CAROL, false, 10), (DAVE, true, 50)]),
+        (&open, vec![(DAVE, true, 80)]),
+    ] {
+        db.store.save_round(round).await.unwrap();
+        for (log_index, (user, is_over, amount)) in (0..).zip(bets) {
+            let bet =
+                market_fixtures::create_test_bet(round, user, is_over, tokens(amount), log_index);
+            db.store.record_bet(&bet).await.unwrap();
+        }
+    }
+
+    for (round, outcome) in [(&first, true), (&second, false)] {
+        let settlement = RoundSettlement {
+            outcome,
+            burned: TokenAmount::zero(),
+            resolved_at: chrono::Utc::now(),
+            payout_multiple_bps: Some(20_000),
+            house_take: TokenAmount::zero(),
+        };
+        db.store.resolve_round(&round.round_id, &settlement).await.unwrap();
+    }
+
+    // Carol lost the first round and Dave the second; Dave's claim on the
+    // open round must not count until it resolves
+    for (round, user, winnings) in [
+        ("1", ALICE, 40),
+        ("1", BOB, 80),
+        ("2", ALICE, 40),
+        ("2", CAROL, 15),
+        ("3", DAVE, 500),
+    ] {
+        let user = EthAddress::from_hex(user).unwrap();
+        db.store.mark_bet_claimed(round, &user, &tokens(winnings)).await.unwrap();
+    }
+}
+
+#[tokio::test]
+async fn test_dead_pool_winners_leaderboard() {
+    let db = TestDb::new().await;
+    seed_dead_pool(&db).await;
+
+    let entries = db
+        .store
+        .get_leaderboard(LeaderboardType::DeadPoolWinners, 10)
+        .await
+        .unwrap();
+
+    // Winnings add up across rounds, and ties are ordered by address
+    assert_eq!(
+        ranking(&entries),
+        [
+            (1, "0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".into(), tokens(80)),
+            (2, "0xbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb".into(), tokens(80)),
+            (3, "0xcccccccccccccccccccccccccccccccccccccccc".into(), tokens(15)),
+        ]
+    );
+    assert_eq!(entries[0].metadata, None);
+
+    let top = db
+        .store
+        .get_leaderboard(LeaderboardType::DeadPoolWinners, 1)
+        .await
+        .unwrap();
+    assert_eq!(ranking(&top), ranking(&entries[..1]));
+}
+
+#[tokio::test]
+async fn test_leaderboard_refresher_serves_from_cache() {
+    let db = TestDb::new().await;
+    seed_leaderboards(&db).await;
+
+    let cache = Arc::new(MemoryCache::new());
+    let refresher = LeaderboardRefresher::new(
+        Arc::new(db.store.clone()),
+        Arc::clone(&cache),
+        &LeaderboardSettings::default(),
+    );
+    assert_eq!(refresher.refresh_all().await, LeaderboardType::ALL.len());
+
+    // New data is not visible until the next refresh
+    let mut late = position_fixtures::create_test_position(
+        "0x0000000000000000000000000000000000000001",
+        Level::Vault,
+    );
+    late.ghost_streak = GhostStreak::new(99).unwrap();
+    db.store.save_position(&late).await.unwrap();
+
+    let hits_before = cache.stats().hits;
+    let cached = refresher.get(LeaderboardType::GhostStreak, 1).await.unwrap();
+    assert_eq!(cache.stats().hits, hits_before + 1);
+    assert_eq!(cached[0].user_address.to_hex(), "0x1111111111111111111111111111111111111111");
+
+    refresher.refresh(LeaderboardType::GhostStreak).await.unwrap();
+    let current = refresher.get(LeaderboardType::GhostStreak, 1).await.unwrap();
+    assert_eq!(current[0].user_address.to_hex(), "0x0000000000000000000000000000000000000001");
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TOKEN FLOW STORE TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+fn flow(burned: u128, tax_collected: u128, holder: EthAddress, outflow: u128) -> TokenFlowDelta {
+    let mut delta = TokenFlowDelta {
+        burned: Some(tokens(burned)),
+        tax_collected: Some(tokens(tax_collected)),
+        transfers: 1,
+        ..TokenFlowDelta::default()
+    };
+    delta.addresses.insert(
+        holder,
+        AddressFlowDelta {
+            outflow: tokens(outflow),
+            transfers_out: 1,
+            ..AddressFlowDelta::default()
+        },
+    );
+    delta
+}
+
+#[tokio::test]
+async fn test_token_flows_accumulate_per_bucket() {
+    let db = TestDb::new().await;
+    let holder = EthAddress::from_hex("0x1111111111111111111111111111111111111111").unwrap();
+    let now = chrono::Utc::now();
+    let current = now.duration_trunc(chrono::TimeDelta::hours(1)).unwrap();
+    let old = current - chrono::TimeDelta::hours(5);
+
+    db.store
+        .record_token_flows(current, &flow(9, 1, holder, 100))
+        .await
+        .unwrap();
+    db.store
+        .record_token_flows(current, &flow(30, 0, holder, 30))
+        .await
+        .unwrap();
+    db.store
+        .record_token_flows(old, &flow(50, 5, holder, 50))
+        .await
+        .unwrap();
+
+    let hour = db.store.get_burn_rate(Duration::from_secs(3600)).await.unwrap();
+    assert_eq!(hour.burned, tokens(39));
+    assert_eq!(hour.tax_collected, tokens(1));
+
+    let day = db.store.get_burn_rate(Duration::from_secs(86_400)).await.unwrap();
+    assert_eq!(day.burned, tokens(89));
+    assert_eq!(day.window_secs, 86_400);
+
+    let flows = db
+        .store
+        .get_address_flows(&holder, Duration::from_secs(3600))
+        .await
+        .unwrap();
+    assert_eq!(flows.outflow, tokens(130));
+    assert_eq!(flows.inflow, TokenAmount::zero());
+    assert_eq!(flows.transfers_out, 2);
+}
+
+#[tokio::test]
+async fn test_record_transfer_is_idempotent() {
+    let db = TestDb::new().await;
+    let transfer = TokenTransfer {
+        from_address: EthAddress::from_hex("0x1111111111111111111111111111111111111111").unwrap(),
+        to_address: EthAddress::from_hex("0x2222222222222222222222222222222222222222").unwrap(),
+        amount: tokens(5),
+        block_number: BlockNumber::new(42),
+        tx_hash: [7u8; 32],
+        log_index: 3,
+        timestamp: chrono::Utc::now(),
+    };
+
+    db.store.record_transfer(&transfer).await.unwrap();
+    db.store.record_transfer(&transfer).await.unwrap();
+
+    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM token_transfers")
+        .fetch_one(&db.pool)
+        .await
+        .unwrap();
+    assert_eq!(count, 1);
+}
+
+#[tokio::test]
+async fn test_holder_balances_rank_and_reconcile() {
+    let db = TestDb::new().await;
+    let now = chrono::Utc::now();
+    for (byte, balance, block) in [(0x11, 5u64, 10u64), (0x22, 9, 11), (0x33, 0, 12)] {
+        let mut holder = HolderBalance::new(EthAddress::new([byte; 20]), now);
+        holder.credit(U256::from(balance));
+        holder.mark_applied(BlockNumber::new(block), 0, now);
+        db.store.save_holder(&holder).await.unwrap();
+    }
+
+    let top = db
+        .store
+        .get_top_holders(HolderSort::Balance, 10)
+        .await
+        .unwrap();
+    let balances: Vec<_> = top.iter().map(|holder| holder.balance).collect();
+    assert_eq!(balances, [U256::from(9), U256::from(5)]);
+    assert_eq!(db.store.count_holders().await.unwrap(), 2);
+
+    // A transfer applied after the sample was read wins over the correction
+    let sampled = db
+        .store
+        .get_holder(&EthAddress::new([0x11; 20]))
+        .await
+        .unwrap()
+        .unwrap();
+    let mut newer = sampled.clone();
+    newer.credit(U256::from(1));
+    newer.mark_applied(BlockNumber::new(13), 4, now);
+    db.store.save_holder(&newer).await.unwrap();
+    let stale = db
+        .store
+        .reconcile_holder(&sampled, U256::from(7), now)
+        .await
+        .unwrap();
+    assert!(!stale);
+
+    assert!(
+        db.store
+            .reconcile_holder(&newer, U256::from(7), now)
+            .await
+            .unwrap()
+    );
+    let reconciled = db.store.get_holder(&newer.address).await.unwrap().unwrap();
+    assert_eq!(reconciled.balance, U256::from(7));
+    assert!(reconciled.reconciled_at.is_some());
+    // Never compared holders come first in the next sample
+    let sample = db.store.get_holders_to_reconcile(10).await.unwrap();
+    assert_eq!(sample.last().unwrap().address, newer.address);
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// BOOST STORE TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+fn boost(position: &Position, expiry: chrono::DateTime<chrono::Utc>, log_index: u64) -> Boost {
+    Boost {
+        id: Uuid::now_v7(),
+        position_id: position.id,
+        user_address: position.user_address,
+        boost_type: BoostType::DeathReduction,
+        value_bps: 1500,
+        expiry,
+        block_number: BlockNumber::new(100),
+        log_index,
+        created_at: position.entry_timestamp,
+        expired_at: None,
+    }
+}
+
+#[tokio::test]
+async fn test_boosts_lapse_by_expiry_and_position_close() {
+    let db = TestDb::new().await;
+    let now = chrono::Utc::now().duration_trunc(chrono::TimeDelta::seconds(1)).unwrap();
+    let mut position = position_fixtures::create_test_position(
+        "0x1111111111111111111111111111111111111111",
+        Level::Darknet,
+    );
+    db.store.save_position(&position).await.unwrap();
+    let soon = boost(&position, now + chrono::TimeDelta::minutes(1), 0);
+    let later = boost(&position, now + chrono::TimeDelta::hours(1), 1);
+    for boost in [&soon, &later] {
+        db.store.save_boost(boost).await.unwrap();
+    }
+    // A replayed log is not a second boost
+    db.store.save_boost(&boost(&position, now, 0)).await.unwrap();
+    assert_eq!(count_rows(&db, "boosts").await, 2);
+
+    let active = db
+        .store
+        .get_active_boosts(&position.user_address, now)
+        .await
+        .unwrap();
+    let ids: Vec<_> = active.iter().map(|boost| boost.id).collect();
+    assert_eq!(ids, [soon.id, later.id]);
+    let expiring = db
+        .store
+        .get_expiring_boosts(now, now + chrono::TimeDelta::minutes(5), 10)
+        .await
+        .unwrap();
+    assert_eq!(expiring.len(), 1);
+
+    // Past the first expiry only that boost lapsed
+    let after = now + chrono::TimeDelta::minutes(2);
+    let lapsed = db.store.get_lapsed_boosts(after, 10).await.unwrap();
+    assert_eq!(lapsed.len(), 1);
+    assert_eq!(lapsed[0].boost.id, soon.id);
+    assert_eq!(lapsed[0].ended_at(), soon.expiry);
+    let expired = [(soon.id, lapsed[0].ended_at())];
+    assert_eq!(db.store.expire_boosts(&expired).await.unwrap(), 1);
+    assert_eq!(db.store.expire_boosts(&expired).await.unwrap(), 0);
+    assert!(db.store.get_lapsed_boosts(after, 10).await.unwrap().is_empty());
+
+    // Closing the position ends the other boost early
+    let exited = now + chrono::TimeDelta::minutes(3);
+    position.close(ExitReason::Extracted, exited, 0);
+    position.is_extracted = true;
+    db.store.save_position(&position).await.unwrap();
+    assert!(
+        db.store
+            .get_active_boosts(&position.user_address, exited)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    let lapsed = db.store.get_lapsed_boosts(exited, 10).await.unwrap();
+    assert_eq!(lapsed.len(), 1);
+    assert_eq!(lapsed[0].boost.id, later.id);
+    assert!(lapsed[0].cut_short());
+    assert_eq!(lapsed[0].ended_at(), exited);
+}
+
+#[tokio::test]
+async fn test_multipliers_weight_the_effective_stake() {
+    let db = TestDb::new().await;
+    let now = chrono::Utc::now().duration_trunc(chrono::TimeDelta::seconds(1)).unwrap();
+    let data = |tokens: &str| TokenAmount::parse(tokens).unwrap();
+    let boosted = position_fixtures::create_test_position(
+        "0x1111111111111111111111111111111111111111",
+        Level::Darknet,
+    );
+    let mut larger = position_fixtures::create_test_position(
+        "0x2222222222222222222222222222222222222222",
+        Level::Darknet,
+    );
+    larger.amount = data("1.2");
+    larger.effective_stake = data("1.2");
+    for position in [&boosted, &larger] {
+        db.store.save_position(position).await.unwrap();
+    }
+    let multiplier = |value_bps, expiry, log_index| Boost {
+        boost_type: BoostType::YieldMultiplier,
+        value_bps,
+        ..boost(&boosted, expiry, log_index)
+    };
+    for boost in [
+        multiplier(2000, now + chrono::TimeDelta::minutes(1), 0),
+        multiplier(3000, now + chrono::TimeDelta::hours(1), 1),
+        boost(&boosted, now + chrono::TimeDelta::hours(1), 2),
+    ] {
+        db.store.save_boost(&boost).await.unwrap();
+    }
+
+    // Multipliers stack additively; death reductions do not weight the stake
+    let positions = [boosted.id, larger.id];
+    let changes = db.store.reweigh_positions(&positions, now).await.unwrap();
+    assert_eq!(
+        changes,
+        [EffectiveStakeChange {
+            position_id: boosted.id,
+            level: Level::Darknet,
+            previous: data("1"),
+            current: data("1.5"),
+        }]
+    );
+    assert!(db.store.reweigh_positions(&positions, now).await.unwrap().is_empty());
+
+    // Ranked by effective stake rather than by the raw one
+    let entries = db
+        .store
+        .get_leaderboard(LeaderboardType::EffectiveStake, 10)
+        .await
+        .unwrap();
+    assert_eq!(
+        ranking(&entries),
+        [
+            (1, "0x1111111111111111111111111111111111111111".into(), data("1.5")),
+            (2, "0x2222222222222222222222222222222222222222".into(), data("1.2")),
+        ]
+    );
+
+    // Past the first expiry only the other multiplier applies
+    let after = now + chrono::TimeDelta::minutes(2);
+    let changes = db.store.reweigh_positions(&positions, after).await.unwrap();
+    assert_eq!(changes.len(), 1);
+    assert_eq!((&changes[0].previous, &changes[0].current), (&data("1.5"), &data("1.3")));
+
+    // Recomputing the stats weighs every active position again, as of now
+    let levels = db.store.recompute_level_stats().await.unwrap();
+    let darknet = levels.iter().find(|s| s.level == Level::Darknet).unwrap();
+    assert_eq!(darknet.total_staked, data("2.2"));
+    assert_eq!(darknet.total_effective_staked, data("2.7"));
+    let saved = db.store.get_position_by_id(&boosted.id).await.unwrap().unwrap();
+    assert_eq!(saved.effective_stake, data("1.5"));
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// SURVIVAL STATS TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Close a Darknet position of `user` for `reason` after `secs` and `streak`.
+#[allow(clippy::cast_sign_loss)]
+async fn save_exit(db: &TestDb, user: &str, reason: ExitReason, secs: i64, streak: i32) {
+    let mut position = position_fixtures::create_test_position(user, Level::Darknet);
+    position.ghost_streak = GhostStreak::new(streak).unwrap();
+    let exited = position.entry_timestamp + chrono::TimeDelta::seconds(secs);
+    position.close(reason, exited, streak as u32);
+    position.is_extracted = reason == ExitReason::Extracted;
+    db.store.save_position(&position).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_count_scans_survived() {
+    let db = TestDb::new().await;
+    for block in [1_000, 1_010, 1_020, 1_030] {
+        let mut scan = scan_fixtures::create_finalized_scan(Level::Darknet, 0);
+        scan.finalized_block = Some(BlockNumber::new(block));
+        db.store.save_scan(&scan).await.unwrap();
+    }
+    let mut other = scan_fixtures::create_finalized_scan(Level::Subnet, 0);
+    other.finalized_block = Some(BlockNumber::new(1_015));
+    db.store.save_scan(&other).await.unwrap();
+
+    // Scans in the entry and exit blocks are not survived
+    let survived = db
+        .store
+        .count_scans_survived(Level::Darknet, BlockNumber::new(1_000), BlockNumber::new(1_030))
+        .await
+        .unwrap();
+    assert_eq!(survived, 2);
+}
+
+#[tokio::test]
+async fn test_survival_stats_per_level() {
+    let db = TestDb::new().await;
+    save_exit(
+        &db,
+        "0x1111111111111111111111111111111111111111",
+        ExitReason::Extracted,
+        100,
+        2,
+    )
+    .await;
+    save_exit(&db, "0x2222222222222222222222222222222222222222", ExitReason::Culled, 300, 2).await;
+    save_exit(&db, "0x3333333333333333333333333333333333333333", ExitReason::Traced, 200, 5).await;
+
+    // Still active: not an exit
+    let alive = position_fixtures::create_test_position(
+        "0x4444444444444444444444444444444444444444",
+        Level::Darknet,
+    );
+    db.store.save_position(&alive).await.unwrap();
+
+    // Exit without an indexed entry: no level, no survival
+    let unknown = Position::unknown_entry(
+        EthAddress::from_hex("0x5555555555555555555555555555555555555555").unwrap(),
+        tokens(1),
+        ExitReason::Culled,
+        chrono::Utc::now(),
+        BlockNumber::new(2_000),
+    );
+    db.store.save_position(&unknown).await.unwrap();
+
+    let levels = db.store.get_survival_stats().await.unwrap();
+    assert_eq!(levels.len(), 1);
+    let darknet = &levels[0];
+    assert_eq!(darknet.level, Level::Darknet);
+    assert_eq!(darknet.exits, 3);
+    assert_eq!(darknet.culled, 1);
+    assert!((darknet.cull_rate - 1.0 / 3.0).abs() < 1e-9);
+    assert_eq!(darknet.average_survival_seconds, Some(200.0));
+    assert_eq!(darknet.average_scans_survived, Some(3.0));
+
+    let streaks = db.store.get_exit_streak_distribution().await.unwrap();
+    let buckets: Vec<_> = streaks
+        .iter()
+        .map(|s| (s.level, s.ghost_streak.value(), s.exits))
+        .collect();
+    assert_eq!(buckets, [(Level::Darknet, 2, 2), (Level::Darknet, 5, 1)]);
+}
+
+#[tokio::test]
+async fn test_recompute_level_stats_counts_survival() {
+    let db = TestDb::new().await;
+    save_exit(&db, "0x1111111111111111111111111111111111111111", ExitReason::Culled, 120, 0).await;
+    save_exit(
+        &db,
+        "0x2222222222222222222222222222222222222222",
+        ExitReason::Extracted,
+        60,
+        0,
+    )
+    .await;
+
+    let levels = db.store.recompute_level_stats().await.unwrap();
+    let darknet = levels.iter().find(|s| s.level == Level::Darknet).unwrap();
+    assert_eq!(darknet.total_culled, 1);
+    assert_eq!(darknet.total_extracted, 1);
+    assert_eq!(darknet.total_survival_seconds, 180);
+    assert_eq!(darknet.survival_samples, 2);
+    assert_eq!(darknet.average_survival_seconds(), Some(90.0));
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// LEVEL HISTORY TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+fn level_scan(id: &str, at: &str, deaths: u32, survivors: u32, block: u64) -> LevelScanStats {
+    LevelScanStats {
+        scan_id: id.to_string(),
+        level: Level::Darknet,
+        scanned_at: at.parse().unwrap(),
+        block_number: Some(BlockNumber::new(block)),
+        deaths,
+        survivors,
+        total_staked: tokens(u128::from(survivors) * 10),
+    }
+}
+
+#[tokio::test]
+async fn test_level_history_buckets_match_the_series() {
+    let db = TestDb::new().await;
+    let scans = [
+        level_scan("1", "2026-01-01T02:00:00Z", 3, 7, 100),
+        level_scan("2", "2026-01-01T20:00:00Z", 1, 9, 110),
+        level_scan("3", "2026-01-03T05:00:00Z", 0, 12, 120),
+    ];
+    db.store.record_level_scans(&scans).await.unwrap();
+    // Recording a scan again is a no-op
+    db.store.record_level_scans(&scans[..1]).await.unwrap();
+
+    let (from, to) = (
+        "2026-01-01T00:00:00Z".parse().unwrap(),
+        "2026-01-04T00:00:00Z".parse().unwrap(),
+    );
+    for bucket in HistoryBucket::ALL {
+        let stored = db
+            .store
+            .get_level_history(Level::Darknet, from, to, bucket)
+            .await
+            .unwrap();
+        assert_eq!(stored, LevelHistoryPoint::series(&scans, from, to, bucket), "{bucket}");
+    }
+    let empty = db
+        .store
+        .get_level_history(Level::BlackIce, from, to, HistoryBucket::Day)
+        .await
+        .unwrap();
+    assert!(empty.iter().all(|point| point.scans == 0 && point.tvl.is_none()));
+
+    // Scans finalized in orphaned blocks are dropped
+    db.store
+        .execute_reorg_rollback(BlockNumber::new(115))
+        .await
+        .unwrap();
+    let days = db
+        .store
+        .get_level_history(Level::Darknet, from, to, HistoryBucket::Day)
+        .await
+        .unwrap();
+    let scanned: Vec<_> = days.iter().map(|point| point.scans).collect();
+    assert_eq!(scanned, [2, 0, 0]);
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// EVENT OUTBOX STORE TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+fn outbox_event(aggregate: &str, block: u64) -> OutboxEvent {
+    OutboxEvent {
+        topic: "positions".into(),
+        aggregate: aggregate.into(),
+        event_type: "JackedIn".into(),
+        payload: b"{}".to_vec(),
+        block_number: BlockNumber::new(block),
+    }
+}
+
+#[tokio::test]
+async fn test_outbox_events_commit_with_position() {
+    let db = TestDb::new().await;
+
+    let position = position_fixtures::create_test_position(
+        "0x8888888888888888888888888888888888888888",
+        Level::Vault,
+    );
+    let entry = PositionHistoryEntry::new(
+        &position,
+        PositionAction::JackedIn,
+        position.amount.clone(),
+        BlockNumber::new(100),
+        position.entry_timestamp,
+    );
+    let events = [outbox_event("position:a", 100), outbox_event("position:a", 100)];
+    db.store
+        .save_position_with_history(&position, &entry, &events)
+        .await
+        .unwrap();
+
+    let unpublished = db.store.fetch_unpublished(10, chrono::Utc::now()).await.unwrap();
+    assert_eq!(unpublished.len(), 2);
+    assert!(unpublished[0].id < unpublished[1].id);
+    assert_eq!(unpublished[0].event, events[0]);
+    assert_eq!(db.store.outbox_depth().await.unwrap(), 2);
+}
+
+#[tokio::test]
+async fn test_outbox_failure_holds_back_only_its_aggregate() {
+    let db = TestDb::new().await;
+    db.store
+        .enqueue_events(&[
+            outbox_event("position:a", 1),
+            outbox_event("position:b", 1),
+            outbox_event("position:a", 2),
+        ])
+        .await
+        .unwrap();
+
+    let now = chrono::Utc::now();
+    let records = db.store.fetch_unpublished(10, now).await.unwrap();
+    let retry_at = now + chrono::Duration::seconds(30);
+    db.store
+        .record_publish_failure(records[0].id, "unavailable", retry_at)
+        .await
+        .unwrap();
+
+    let due = db.store.fetch_unpublished(10, now).await.unwrap();
+    assert_eq!(due.iter().map(|r| r.id).collect::<Vec<_>>(), [records[1].id]);
+
+    let retried = db.store.fetch_unpublished(10, retry_at).await.unwrap();
+    assert_eq!(retried.len(), 3);
+    assert_eq!(retried[0].attempts, 1);
+}
+
+#[tokio::test]
+async fn test_outbox_mark_and_purge() {
+    let db = TestDb::new().await;
+    db.store
+        .enqueue_events(&[outbox_event("position:a", 1), outbox_event("position:b", 1)])
+        .await
+        .unwrap();
+
+    let now = chrono::Utc::now();
+    let records = db.store.fetch_unpublished(10, now).await.unwrap();
+    db.store.mark_published(&[records[0].id], now).await.unwrap();
+
+    assert_eq!(db.store.outbox_depth().await.unwrap(), 1);
+    assert_eq!(db.store.purge_published(now).await.unwrap(), 0);
+    let later = now + chrono::Duration::hours(1);
+    assert_eq!(db.store.purge_published(later).await.unwrap(), 1);
+
+    let remaining = db.store.fetch_unpublished(10, later).await.unwrap();
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(remaining[0].id, records[1].id);
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// WRITE BATCHING TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Write a position with its history, a death and a transfer at `block`.
+#[allow(clippy::cast_possible_truncation)]
+async fn write_block(store: &PostgresStore, user: &str, block: u64) -> Position {
+    let position = position_fixtures::create_test_position(user, Level::Darknet);
+    let entry = PositionHistoryEntry::new(
+        &position,
+        PositionAction::JackedIn,
+        position.amount.clone(),
+        BlockNumber::new(block),
+        position.entry_timestamp,
+    );
+    store
+        .save_position_with_history(&position, &entry, &[outbox_event("position:b", block)])
+        .await
+        .unwrap();
+    store
+        .record_deaths(&[death_fixtures::create_test_death(user, Level::Darknet, 1)])
+        .await
+        .unwrap();
+    store
+        .record_transfer(&TokenTransfer {
+            from_address: position.user_address,
+            to_address: EthAddress::from_hex("0x2222222222222222222222222222222222222222").unwrap(),
+            amount: tokens(1),
+            block_number: BlockNumber::new(block),
+            tx_hash: [block as u8; 32],
+            log_index: 0,
+            timestamp: chrono::Utc::now(),
+        })
+        .await
+        .unwrap();
+    position
+}
+
+async fn count_rows(db: &TestDb, table: &str) -> i64 {
+    sqlx::query_scalar(&format!("SELECT COUNT(*) FROM {table}"))
+        .fetch_one(&db.pool)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_batched_writes_commit_with_checkpoint() {
+    let db = TestDb::new().await;
+    let batched = db.store.batched();
+    let user = "0xb000000000000000000000000000000000000001";
+
+    let position = write_block(&batched, user, 100).await;
+    batched
+        .set_last_block(BlockNumber::new(100), B256::repeat_byte(1))
+        .await
+        .unwrap();
+
+    // The batch reads its own writes, including the buffered rows
+    let history = batched.get_history(&position.user_address, 10, None).await.unwrap();
+    assert_eq!(history.len(), 1);
+    assert_eq!(batched.get_user_deaths(&position.user_address, 10).await.unwrap().len(), 1);
+
+    // Nothing is visible outside the batch before it commits
+    assert!(db.store.get_position_by_id(&position.id).await.unwrap().is_none());
+    assert_eq!(db.store.get_last_block().await.unwrap(), BlockNumber::new(0));
+
+    batched.commit_batch().await.unwrap();
+
+    assert!(db.store.get_position_by_id(&position.id).await.unwrap().is_some());
+    assert_eq!(db.store.get_last_block().await.unwrap(), BlockNumber::new(100));
+    assert_eq!(count_rows(&db, "position_history").await, 1);
+    assert_eq!(count_rows(&db, "deaths").await, 1);
+    assert_eq!(count_rows(&db, "token_transfers").await, 1);
+    assert_eq!(db.store.outbox_depth().await.unwrap(), 1);
+}
+
+#[tokio::test]
+async fn test_failed_batch_persists_nothing() {
+    let db = TestDb::new().await;
+    let batched = db.store.batched();
+    let user = "0xb000000000000000000000000000000000000002";
+
+    let position = write_block(&batched, user, 200).await;
+    // A death recorded twice violates the primary key mid-batch
+    let death = death_fixtures::create_test_death(user, Level::Darknet, 1);
+    batched.record_deaths(&[death.clone(), death]).await.unwrap();
+    batched
+        .set_last_block(BlockNumber::new(200), B256::repeat_byte(2))
+        .await
+        .unwrap();
+
+    assert!(batched.commit_batch().await.is_err());
+
+    assert!(db.store.get_position_by_id(&position.id).await.unwrap().is_none());
+    assert_eq!(db.store.get_last_block().await.unwrap(), BlockNumber::new(0));
+    assert_eq!(count_rows(&db, "position_history").await, 0);
+    assert_eq!(count_rows(&db, "deaths").await, 0);
+    assert_eq!(count_rows(&db, "token_transfers").await, 0);
+    assert_eq!(db.store.outbox_depth().await.unwrap(), 0);
+
+    // The next batch starts afresh
+    write_block(&batched, user, 201).await;
+    batched.commit_batch().await.unwrap();
+    assert_eq!(count_rows(&db, "deaths").await, 1);
+}
+
+#[tokio::test]
+async fn test_failed_statement_aborts_batch() {
+    let db = TestDb::new().await;
+    let batched = db.store.batched();
+    let user = "0xb000000000000000000000000000000000000003";
+
+    let position = write_block(&batched, user, 300).await;
+    // Reading the deaths flushes the duplicate into the open transaction
+    let death = death_fixtures::create_test_death(user, Level::Darknet, 1);
+    batched.record_deaths(&[death.clone(), death]).await.unwrap();
+    assert!(batched.get_user_deaths(&position.user_address, 10).await.is_err());
+
+    // Later writes of the batch fail too, and so does its commit
+    assert!(batched.save_position(&position).await.is_err());
+    assert!(batched.commit_batch().await.is_err());
+    assert!(db.store.get_position_by_id(&position.id).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_discarded_batch_rolls_back() {
+    let db = TestDb::new().await;
+    let batched = db.store.batched();
+
+    let position = write_block(&batched, "0xb000000000000000000000000000000000000004", 400).await;
+    batched.discard_batch().await.unwrap();
+    batched.commit_batch().await.unwrap();
+
+    assert!(db.store.get_position_by_id(&position.id).await.unwrap().is_none());
+    assert_eq!(count_rows(&db, "position_history").await, 0);
+    assert_eq!(count_rows(&db, "deaths").await, 0);
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // TIMESCALEDB-SPECIFIC TESTS
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -0,0 +1,252 @@
+//! Integration tests for verifying indexed state against the chain.
+//!
+//! These tests seed a real TimescaleDB instance in Docker with positions
+//! that diverge from a mocked `GhostCore`, then check that the divergence is
+//! found and that fixing it makes the database match the chain.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+#[allow(dead_code)] // Not every test binary uses every shared fixture
+mod common;
+
+use std::sync::Arc;
+
+use alloy::primitives::{Address, Bytes, U256};
+use alloy::providers::ProviderBuilder;
+use alloy::providers::mock::Asserter;
+use alloy::sol;
+use alloy::sol_types::SolValue;
+
+use common::fixtures::{TestDb, position_fixtures};
+use ghostnet_indexer::abi::ghost_core;
+use ghostnet_indexer::indexer::{Severity, StateVerifier, Subject};
+use ghostnet_indexer::ports::{PositionStore, StatsStore};
+use ghostnet_indexer::store::PostgresStore;
+use ghostnet_indexer::types::enums::Level;
+use ghostnet_indexer::types::primitives::{EthAddress, TokenAmount};
+
+sol! {
+    struct Result3 {
+        bool success;
+        bytes returnData;
+    }
+}
+
+const MATCHING: &str = "0x1111111111111111111111111111111111111111";
+const DIVERGING: &str = "0x2222222222222222222222222222222222222222";
+const DEAD: &str = "0x3333333333333333333333333333333333333333";
+
+fn data(tokens: u64) -> U256 {
+    U256::from(tokens) * U256::from(10u64).pow(U256::from(18))
+}
+
+/// An `aggregate3` response in which every call succeeded.
+fn aggregate(returns: Vec<Vec<u8>>) -> Bytes {
+    let results: Vec<_> = returns
+        .into_iter()
+        .map(|data| Result3 {
+            success: true,
+            returnData: data.into(),
+        })
+        .collect();
+    results.abi_encode().into()
+}
+
+fn position(level: Level, tokens: u64, streak: u16, alive: bool) -> Vec<u8> {
+    ghost_core::Position {
+        amount: data(tokens),
+        level: u8::from(level),
+        entryTimestamp: 1_700_000_000,
+        lastAddTimestamp: 0,
+        rewardDebt: U256::ZERO,
+        alive,
+        ghostStreak: streak,
+    }
+    .abi_encode()
+}
+
+fn level_state(tokens: u64, alive: u64) -> Vec<u8> {
+    ghost_core::LevelState {
+        totalStaked: data(tokens),
+        aliveCount: U256::from(alive),
+        accRewardsPerShare: U256::ZERO,
+        nextScanTime: 0,
+    }
+    .abi_encode()
+}
+
+/// Queue the chain's answers to one verification run.
+///
+/// On chain, the diverging position holds 3 DATA with a streak of 2, and
+/// the dead one was traced.
+fn push_chain_state(asserter: &Asserter) {
+    asserter.push_success(&aggregate(vec![
+        position(Level::Vault, 1, 0, true),
+        position(Level::Subnet, 3, 2, true),
+        position(Level::Darknet, 1, 0, false),
+    ]));
+    asserter.push_success(&aggregate(vec![
+        level_state(1, 1),
+        level_state(0, 0),
+        level_state(3, 1),
+        level_state(0, 0),
+        level_state(0, 0),
+    ]));
+    asserter.push_success(&aggregate(vec![data(4).abi_encode()]));
+}
+
+/// Index one position per user, each at its own level, with 1 DATA.
+async fn seed(store: &PostgresStore) {
+    for (user, level) in [
+        (MATCHING, Level::Vault),
+        (DIVERGING, Level::Subnet),
+        (DEAD, Level::Darknet),
+    ] {
+        let position = position_fixtures::create_test_position(user, level);
+        store.save_position(&position).await.unwrap();
+    }
+    store.recompute_level_stats().await.unwrap();
+    store.refresh_global_stats().await.unwrap();
+}
+
+fn verifier(db: &TestDb, asserter: &Asserter) -> StateVerifier<PostgresStore> {
+    let provider = ProviderBuilder::new().connect_mocked_client(asserter.clone());
+    StateVerifier::new(
+        Arc::new(PostgresStore::clone(&db.store)),
+        provider,
+        Address::repeat_byte(0xc0),
+    )
+}
+
+#[tokio::test]
+async fn test_verify_detects_seeded_divergence() {
+    let db = TestDb::new().await;
+    seed(&db.store).await;
+    let asserter = Asserter::new();
+    push_chain_state(&asserter);
+
+    let report = verifier(&db, &asserter).verify(None).await.unwrap();
+
+    assert_eq!(report.positions_checked, 3);
+    let found: Vec<_> = report
+        .mismatches()
+        .iter()
+        .map(|m| (m.subject.to_string(), m.field, m.severity))
+        .collect();
+    let diverging = EthAddress::from_hex(DIVERGING).unwrap();
+    let dead = EthAddress::from_hex(DEAD).unwrap();
+    for expected in [
+        (Subject::Position(diverging), "amount", Severity::Critical),
+        (
+            Subject::Position(diverging),
+            "ghost_streak",
+            Severity::Major,
+        ),
+        (Subject::Position(dead), "is_alive", Severity::Critical),
+        (
+            Subject::Level(Level::Subnet),
+            "total_staked",
+            Severity::Minor,
+        ),
+        (
+            Subject::Level(Level::Darknet),
+            "alive_count",
+            Severity::Minor,
+        ),
+        (Subject::Global, "total_value_locked", Severity::Minor),
+    ] {
+        let expected = (expected.0.to_string(), expected.1, expected.2);
+        assert!(
+            found.contains(&expected),
+            "missing {expected:?} in {found:?}"
+        );
+    }
+    let matching = Subject::Position(EthAddress::from_hex(MATCHING).unwrap()).to_string();
+    assert!(
+        !found.iter().any(|(subject, ..)| *subject == matching),
+        "matching position reported: {found:?}"
+    );
+    assert_eq!(report.count_at_least(Severity::Critical), 2);
+    assert_eq!(report.mismatches()[0].severity, Severity::Critical);
+}
+
+#[tokio::test]
+async fn test_verify_sample_limits_positions_checked() {
+    let db = TestDb::new().await;
+    seed(&db.store).await;
+    let asserter = Asserter::new();
+    // Whichever position is sampled, it reads as alive at its indexed level
+    asserter.push_success(&aggregate(vec![position(Level::Vault, 1, 0, true)]));
+    asserter.push_success(&aggregate(vec![level_state(1, 1); 5]));
+    asserter.push_success(&aggregate(vec![data(3).abi_encode()]));
+
+    let report = verifier(&db, &asserter).verify(Some(1)).await.unwrap();
+
+    assert_eq!(report.positions_checked, 1);
+}
+
+#[tokio::test]
+async fn test_fix_overwrites_divergence_and_journals_it() {
+    let db = TestDb::new().await;
+    seed(&db.store).await;
+    let asserter = Asserter::new();
+    push_chain_state(&asserter);
+    let verifier = verifier(&db, &asserter);
+    let report = verifier.verify(None).await.unwrap();
+
+    let journal = verifier.fix(&report).await.unwrap();
+
+    // Rows now hold their on-chain state
+    let diverging = EthAddress::from_hex(DIVERGING).unwrap();
+    let fixed = db
+        .store
+        .get_active_position(&diverging)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(fixed.amount, TokenAmount::from_wei(data(3), 18));
+    assert_eq!(fixed.ghost_streak.value(), 2);
+    let dead = EthAddress::from_hex(DEAD).unwrap();
+    assert!(db.store.get_active_position(&dead).await.unwrap().is_none());
+    let subnet = db.store.get_level_stats(Level::Subnet).await.unwrap();
+    assert_eq!(subnet.total_staked, TokenAmount::from_wei(data(3), 18));
+
+    // Every overwritten value is journaled
+    assert_eq!(journal.len(), report.mismatches().len());
+    let amount = journal
+        .iter()
+        .find(|entry| entry.subject == Subject::Position(diverging) && entry.field == "amount")
+        .unwrap();
+    assert_eq!(
+        TokenAmount::parse(&amount.before).unwrap(),
+        TokenAmount::from_wei(data(1), 18)
+    );
+    assert_eq!(
+        TokenAmount::parse(&amount.after).unwrap(),
+        TokenAmount::from_wei(data(3), 18)
+    );
+    let tvl = journal
+        .iter()
+        .find(|entry| entry.subject == Subject::Global)
+        .unwrap();
+    assert_eq!(
+        TokenAmount::parse(&tvl.after).unwrap(),
+        TokenAmount::from_wei(data(4), 18)
+    );
+
+    // Verifying again finds nothing, the dead position no longer being active
+    asserter.push_success(&aggregate(vec![
+        position(Level::Vault, 1, 0, true),
+        position(Level::Subnet, 3, 2, true),
+    ]));
+    asserter.push_success(&aggregate(vec![
+        level_state(1, 1),
+        level_state(0, 0),
+        level_state(3, 1),
+        level_state(0, 0),
+        level_state(0, 0),
+    ]));
+    asserter.push_success(&aggregate(vec![data(4).abi_encode()]));
+    let report = verifier.verify(None).await.unwrap();
+    assert!(report.is_consistent(), "{:?}", report.mismatches());
+}